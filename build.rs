@@ -0,0 +1,18 @@
+//! Compiles the gRPC service definitions in `proto/` when the `grpc`
+//! feature is enabled. Other builds have nothing to do here.
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    #[cfg(feature = "grpc")]
+    {
+        println!("cargo:rerun-if-changed=proto");
+        let protoc = protoc_bin_vendored::protoc_bin_path().expect("vendored protoc is available");
+        // SAFETY: build scripts are single-threaded
+        unsafe { std::env::set_var("PROTOC", protoc) };
+        tonic_prost_build::configure()
+            .build_client(false)
+            .compile_protos(&["proto/hadrian/v1/gateway.proto"], &["proto"])
+            .expect("failed to compile gRPC protos");
+    }
+}
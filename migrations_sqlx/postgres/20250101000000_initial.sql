@@ -8,6 +8,24 @@ CREATE TABLE IF NOT EXISTS organizations (
     id UUID PRIMARY KEY NOT NULL,
     slug VARCHAR(64) NOT NULL UNIQUE,
     name VARCHAR(255) NOT NULL,
+    -- Data residency policy (JSON: {"allowed_regions": [...]}), NULL = unrestricted
+    data_residency JSONB,
+    -- Default model/temperature/system prompt applied when clients omit them (JSON), NULL = none
+    request_defaults JSONB,
+    -- Conversation title/summary generation settings (JSON: {"enabled": ..., "model": ...}), NULL = gateway default
+    conversation_summaries JSONB,
+    -- Network ranges the org's API keys may be used from (JSON: {"ip_allowlist": [...]}), NULL = unrestricted
+    network_policy JSONB,
+    -- Parameter bounds for the org's requests (JSON: {"rules": [...]}), NULL = ungoverned
+    parameter_governance JSONB,
+    -- Model/provider allow and deny lists (JSON: {"allowed_models": [...], ...}), NULL = unrestricted
+    model_access JSONB,
+    -- Cheaper models to downgrade to under pressure (JSON: {"ladders": [...]}), NULL = never downgrade
+    model_degradation JSONB,
+    -- Plan and per-feature overrides (JSON: {"plan": "team", "file_search": true, ...}), NULL = default plan
+    entitlements JSONB,
+    -- Free-form labels for grouping and list filtering (JSON object: {"env": "prod"})
+    labels JSONB NOT NULL DEFAULT '{}',
     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
     updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
     deleted_at TIMESTAMPTZ
@@ -50,6 +68,14 @@ CREATE TABLE IF NOT EXISTS projects (
     team_id UUID REFERENCES teams(id) ON DELETE SET NULL,
     slug VARCHAR(64) NOT NULL,
     name VARCHAR(255) NOT NULL,
+    -- Default model/temperature/system prompt overriding the org defaults (JSON), NULL = inherit
+    request_defaults JSONB,
+    -- Parameter bounds applied after the org's (JSON: {"rules": [...]}), NULL = org rules only
+    parameter_governance JSONB,
+    -- Model/provider allow and deny lists checked with the org's (JSON), NULL = org policy only
+    model_access JSONB,
+    -- Free-form labels for grouping and list filtering (JSON object: {"env": "prod"})
+    labels JSONB NOT NULL DEFAULT '{}',
     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
     updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
     deleted_at TIMESTAMPTZ,
@@ -281,6 +307,8 @@ CREATE TABLE IF NOT EXISTS org_sso_configs (
     allowed_email_domains JSONB,
     sync_attributes_on_login BOOLEAN NOT NULL DEFAULT FALSE,
     sync_memberships_on_login BOOLEAN NOT NULL DEFAULT TRUE,
+    -- JSON array of claim-based role rules evaluated during JIT provisioning
+    role_rules JSONB,
 
     -- ==========================================================================
     -- Status & Enforcement
@@ -524,6 +552,41 @@ CREATE INDEX IF NOT EXISTS idx_org_rbac_policy_versions_latest ON org_rbac_polic
 -- Index for cleanup jobs finding old versions by creation date
 CREATE INDEX IF NOT EXISTS idx_org_rbac_policy_versions_cleanup ON org_rbac_policy_versions(policy_id, created_at);
 
+-- ======================================================================
+-- Organization Request Policies
+-- ======================================================================
+
+DO $$ BEGIN
+    CREATE TYPE request_policy_action AS ENUM ('allow', 'deny', 'modify');
+EXCEPTION
+    WHEN duplicate_object THEN null;
+END $$;
+
+-- Per-organization CEL policies evaluated against data-plane requests.
+-- action: 'allow' stops evaluation, 'deny' rejects the request, 'modify'
+-- overwrites top-level request fields with `modifications` and continues.
+CREATE TABLE IF NOT EXISTS org_request_policies (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    name VARCHAR(128) NOT NULL,
+    description TEXT,
+    -- CEL expression over `request`, `subject` and `time`
+    condition TEXT NOT NULL,
+    action request_policy_action NOT NULL DEFAULT 'deny',
+    -- Request fields to set (modify only)
+    modifications JSONB,
+    -- Message returned to the caller when the policy denies a request
+    message TEXT,
+    -- Higher priority = evaluated first (descending order)
+    priority INTEGER NOT NULL DEFAULT 0,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE(org_id, name)
+);
+
+CREATE INDEX IF NOT EXISTS idx_org_request_policies_org_priority ON org_request_policies(org_id, priority DESC);
+
 -- ======================================================================
 -- API Keys
 -- ======================================================================
@@ -550,6 +613,10 @@ CREATE TABLE IF NOT EXISTS api_keys (
     name VARCHAR(255) NOT NULL,
     key_hash VARCHAR(64) NOT NULL UNIQUE,
     key_prefix VARCHAR(16) NOT NULL,
+    -- Hash scheme that produced key_hash; see [auth.api_key]
+    hash_scheme VARCHAR(32) NOT NULL DEFAULT 'sha256' CHECK (hash_scheme IN ('sha256', 'hmac_sha256', 'argon2')),
+    -- Salted Argon2id PHC string checked after lookup (argon2 scheme only)
+    key_verifier TEXT,
     -- Budget enforcement
     budget_amount BIGINT,
     budget_period budget_period,
@@ -569,6 +636,8 @@ CREATE TABLE IF NOT EXISTS api_keys (
     expires_at TIMESTAMPTZ,
     last_used_at TIMESTAMPTZ,
     rotation_grace_until TIMESTAMPTZ,
+    -- Free-form labels for grouping and list filtering (JSON object: {"env": "prod"})
+    labels JSONB NOT NULL DEFAULT '{}',
     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
     updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
 );
@@ -609,6 +678,8 @@ CREATE TABLE IF NOT EXISTS dynamic_providers (
     -- Sovereignty metadata (data residency, compliance requirements)
     sovereignty JSONB,
     is_enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    -- Free-form labels for grouping and list filtering (JSON object: {"env": "prod"})
+    labels JSONB NOT NULL DEFAULT '{}',
     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
     updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
     UNIQUE(owner_type, owner_id, name)
@@ -670,7 +741,38 @@ CREATE TABLE IF NOT EXISTS usage_records (
     -- Shell process exit code (only populated for shell tool records).
     -- Kept separate from status_code (HTTP) so a shell that exits non-zero
     -- inside a 200 response is observable in usage queries.
-    tool_exit_code INTEGER
+    tool_exit_code INTEGER,
+    -- Subject (sub claim) of an externally-issued JWT used to authenticate the request
+    jwt_subject VARCHAR(255),
+    -- Error category from the gateway's error taxonomy (rate_limited,
+    -- context_length, ...) for failed requests; NULL on success
+    error_code VARCHAR(32),
+    -- Re-prompts made because a json_schema response failed validation;
+    -- NULL when the response wasn't validated
+    structured_output_repairs INTEGER,
+    -- Estimated prompt tokens before and after context compression; NULL
+    -- when the request wasn't compressed
+    context_original_tokens INTEGER,
+    context_compressed_tokens INTEGER,
+    -- Model the client requested when the request was downgraded along an
+    -- org's degradation ladder, and why (rate_limited, over_budget,
+    -- circuit_open); NULL when it wasn't downgraded
+    degraded_from_model VARCHAR(255),
+    degradation_reason VARCHAR(32),
+    -- Streaming timings: time to first token, mean gap between chunks and
+    -- output tokens per second of generation; NULL for non-streaming requests
+    ttft_ms INTEGER,
+    inter_token_latency_ms DOUBLE PRECISION,
+    output_tokens_per_second DOUBLE PRECISION,
+    -- Idempotency-Key header the client sent, if any
+    idempotency_key VARCHAR(255),
+    -- Requests sent to the provider counting retries, and how many of them
+    -- timed out before a retry (possibly billed upstream as well)
+    provider_attempts INTEGER,
+    suspected_duplicate_attempts INTEGER,
+    -- Cost allocation tags from the X-Hadrian-Tags header
+    -- (JSON: {"feature": "autocomplete", ...}), NULL when untagged
+    tags JSONB
 );
 
 -- API key indexes (partial: only index rows with api_key_id)
@@ -688,6 +790,7 @@ CREATE INDEX IF NOT EXISTS idx_usage_records_recorded_at ON usage_records(record
 CREATE INDEX IF NOT EXISTS idx_usage_records_recorded_at_id ON usage_records(recorded_at DESC, id DESC);
 CREATE INDEX IF NOT EXISTS idx_usage_records_model ON usage_records(model);
 CREATE INDEX IF NOT EXISTS idx_usage_records_request_id ON usage_records(request_id);
+CREATE INDEX IF NOT EXISTS idx_usage_records_error_code ON usage_records(error_code, recorded_at) WHERE error_code IS NOT NULL;
 
 -- ======================================================================
 -- Model Pricing
@@ -867,6 +970,10 @@ CREATE TABLE IF NOT EXISTS conversations (
     -- Message history (JSON array)
     messages JSONB NOT NULL DEFAULT '[]',
     pin_order INTEGER,
+    -- Rolling summary generated by the conversation_summaries job
+    summary TEXT,
+    -- Number of messages covered by the summary
+    summary_message_count INTEGER NOT NULL DEFAULT 0,
     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
     updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
     deleted_at TIMESTAMPTZ
@@ -880,10 +987,34 @@ CREATE INDEX IF NOT EXISTS idx_conversations_owner_active ON conversations(owner
 CREATE INDEX IF NOT EXISTS idx_conversations_owner_pinned ON conversations(owner_type, owner_id, pin_order) WHERE pin_order IS NOT NULL AND deleted_at IS NULL;
 
 DO $$ BEGIN
-    CREATE TRIGGER update_conversations_updated_at BEFORE UPDATE ON conversations FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+    -- Background summary writes are not user activity and must not reorder conversation lists
+    CREATE TRIGGER update_conversations_updated_at BEFORE UPDATE ON conversations FOR EACH ROW
+    WHEN (NEW.summary IS NOT DISTINCT FROM OLD.summary AND NEW.summary_message_count = OLD.summary_message_count)
+    EXECUTE FUNCTION update_updated_at_column();
 EXCEPTION WHEN duplicate_object THEN null;
 END $$;
 
+-- Conversation branches: alternate message histories created by editing or
+-- deleting a message. The root branch is created lazily on the first fork.
+-- conversations.messages always mirrors the active branch so readers that
+-- are unaware of branching keep working.
+-- fork_index: number of messages inherited from the parent branch
+CREATE TABLE IF NOT EXISTS conversation_branches (
+    id UUID PRIMARY KEY NOT NULL,
+    conversation_id UUID NOT NULL REFERENCES conversations(id) ON DELETE CASCADE,
+    parent_branch_id UUID REFERENCES conversation_branches(id) ON DELETE SET NULL,
+    fork_index INTEGER NOT NULL DEFAULT 0,
+    -- Message history for this branch (JSON array)
+    messages JSONB NOT NULL DEFAULT '[]',
+    is_active BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_conversation_branches_conversation ON conversation_branches(conversation_id, created_at);
+-- At most one active branch per conversation
+CREATE UNIQUE INDEX IF NOT EXISTS idx_conversation_branches_active ON conversation_branches(conversation_id) WHERE is_active;
+
 -- ======================================================================
 -- Audit Logs
 -- ======================================================================
@@ -1036,6 +1167,8 @@ CREATE TABLE IF NOT EXISTS vector_stores (
     metadata JSONB,
     -- Expiration policy: {"anchor": "last_active_at", "days": N}
     expires_after JSONB,
+    -- Default hybrid search fusion weights: {"embedding_weight": 1.0, "text_weight": 1.0}
+    hybrid_search JSONB,
     expires_at TIMESTAMPTZ,
     last_active_at TIMESTAMPTZ,
     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
@@ -1126,6 +1259,8 @@ CREATE TABLE IF NOT EXISTS templates (
     content TEXT NOT NULL,
     -- Optional metadata (temperature, max_tokens, etc.)
     metadata JSONB,
+    -- Free-form labels for grouping and list filtering (JSON object: {"env": "prod"})
+    labels JSONB NOT NULL DEFAULT '{}',
     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
     updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
     deleted_at TIMESTAMPTZ,
@@ -1176,6 +1311,30 @@ DO $$ BEGIN
 EXCEPTION WHEN duplicate_object THEN null;
 END $$;
 
+DO $$ BEGIN
+    CREATE TYPE cert_match_type AS ENUM ('fingerprint_sha256', 'san_uri', 'san_dns', 'san_email', 'subject_cn');
+EXCEPTION
+    WHEN duplicate_object THEN null;
+END $$;
+
+-- Client certificate → service account mappings for mTLS authentication.
+-- `match_value` is stored normalized (see CertMatchType::normalize) and is
+-- globally unique per match type so a certificate resolves to at most one
+-- service account per identifier.
+CREATE TABLE IF NOT EXISTS client_cert_mappings (
+    id UUID PRIMARY KEY NOT NULL,
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    service_account_id UUID NOT NULL REFERENCES service_accounts(id) ON DELETE CASCADE,
+    match_type cert_match_type NOT NULL,
+    match_value VARCHAR(1024) NOT NULL,
+    description TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE(match_type, match_value)
+);
+
+CREATE INDEX IF NOT EXISTS idx_client_cert_mappings_service_account
+    ON client_cert_mappings(service_account_id, created_at DESC);
+
 -- ======================================================================
 -- Skills
 -- ======================================================================
@@ -1533,3 +1692,627 @@ CREATE INDEX IF NOT EXISTS idx_mcp_pending_approvals_response
     ON mcp_pending_approvals(response_id);
 CREATE INDEX IF NOT EXISTS idx_mcp_pending_approvals_expires
     ON mcp_pending_approvals(expires_at);
+
+-- ======================================================================
+-- Scheduled report runs
+-- ======================================================================
+
+DO $$ BEGIN
+    CREATE TYPE report_run_status AS ENUM ('delivered', 'failed');
+EXCEPTION
+    WHEN duplicate_object THEN null;
+END $$;
+
+-- One row per attempt to generate and deliver a scheduled report
+-- (`[features.scheduled_reports]`) for an org and reporting period.
+-- Report definitions live in config, so `report_name` is not a foreign key.
+CREATE TABLE IF NOT EXISTS scheduled_report_runs (
+    id UUID PRIMARY KEY NOT NULL,
+    report_name VARCHAR(128) NOT NULL,
+    kind VARCHAR(64) NOT NULL,
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    -- Inclusive reporting period
+    period_start DATE NOT NULL,
+    period_end DATE NOT NULL,
+    format VARCHAR(16) NOT NULL,
+    delivery VARCHAR(16) NOT NULL,
+    status report_run_status NOT NULL,
+    error TEXT,
+    -- Rendered report body, kept so admins can re-download past reports
+    content TEXT,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+-- The scheduler looks up prior attempts by (report, org, period) every tick
+CREATE INDEX IF NOT EXISTS idx_scheduled_report_runs_period
+    ON scheduled_report_runs(report_name, org_id, period_start);
+CREATE INDEX IF NOT EXISTS idx_scheduled_report_runs_org_created
+    ON scheduled_report_runs(org_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_scheduled_report_runs_created
+    ON scheduled_report_runs(created_at DESC);
+
+-- ======================================================================
+-- Vector store syncs
+-- ======================================================================
+
+-- One row per run of a `[features.vector_store_sync]` source. Sources live in
+-- config, so `source_name` is not a foreign key.
+DO $$ BEGIN
+    CREATE TYPE vector_store_sync_status AS ENUM ('running', 'succeeded', 'partially_succeeded', 'failed');
+EXCEPTION
+    WHEN duplicate_object THEN null;
+END $$;
+
+CREATE TABLE IF NOT EXISTS vector_store_syncs (
+    id UUID PRIMARY KEY NOT NULL,
+    vector_store_id UUID NOT NULL REFERENCES vector_stores(id) ON DELETE CASCADE,
+    source_name VARCHAR(128) NOT NULL,
+    connector VARCHAR(32) NOT NULL,
+    status vector_store_sync_status NOT NULL,
+    files_added BIGINT NOT NULL DEFAULT 0,
+    files_updated BIGINT NOT NULL DEFAULT 0,
+    files_deleted BIGINT NOT NULL DEFAULT 0,
+    files_unchanged BIGINT NOT NULL DEFAULT 0,
+    files_failed BIGINT NOT NULL DEFAULT 0,
+    error TEXT,
+    started_at TIMESTAMPTZ NOT NULL,
+    finished_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_vector_store_syncs_store_started
+    ON vector_store_syncs(vector_store_id, started_at DESC);
+-- The scheduler looks up the latest run per source every tick
+CREATE INDEX IF NOT EXISTS idx_vector_store_syncs_source_started
+    ON vector_store_syncs(vector_store_id, source_name, started_at DESC);
+
+-- The version of each source document last ingested, so unchanged documents
+-- are skipped and removed ones can be deleted from the store.
+CREATE TABLE IF NOT EXISTS vector_store_sync_items (
+    vector_store_id UUID NOT NULL REFERENCES vector_stores(id) ON DELETE CASCADE,
+    source_name VARCHAR(128) NOT NULL,
+    source_key TEXT NOT NULL,
+    version TEXT NOT NULL,
+    file_id UUID NOT NULL,
+    synced_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (vector_store_id, source_name, source_key)
+);
+
+-- ─────────────────────────────────────────────────────────────────────────────
+-- federated_gateways / federated_usage
+-- ─────────────────────────────────────────────────────────────────────────────
+-- Written only on a federation hub (`[federation.hub]`). Each satellite gateway
+-- periodically pushes its daily usage totals and provider health; the hub
+-- keeps the latest snapshot per satellite. Satellites are configured in TOML,
+-- so `id` is the satellite's configured ID rather than a generated UUID.
+CREATE TABLE IF NOT EXISTS federated_gateways (
+    id VARCHAR(64) PRIMARY KEY NOT NULL,
+    region VARCHAR(64),
+    version VARCHAR(64) NOT NULL,
+    -- Provider health snapshots from the latest report
+    providers JSONB NOT NULL DEFAULT '[]',
+    first_report_at TIMESTAMPTZ NOT NULL,
+    last_report_at TIMESTAMPTZ NOT NULL
+);
+
+-- Daily per-model totals as reported by each satellite. Satellites resend
+-- whole days, so rows are replaced rather than accumulated.
+CREATE TABLE IF NOT EXISTS federated_usage (
+    gateway_id VARCHAR(64) NOT NULL REFERENCES federated_gateways(id) ON DELETE CASCADE,
+    date DATE NOT NULL,
+    model VARCHAR(255) NOT NULL,
+    total_cost_microcents BIGINT NOT NULL DEFAULT 0,
+    input_tokens BIGINT NOT NULL DEFAULT 0,
+    output_tokens BIGINT NOT NULL DEFAULT 0,
+    total_tokens BIGINT NOT NULL DEFAULT 0,
+    request_count BIGINT NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (gateway_id, date, model)
+);
+
+CREATE INDEX IF NOT EXISTS idx_federated_usage_date
+    ON federated_usage(date);
+
+-- ─────────────────────────────────────────────────────────────────────────────
+-- admin_totp_enrollments
+-- ─────────────────────────────────────────────────────────────────────────────
+-- TOTP authenticators used for admin step-up authentication
+-- (`[auth.step_up]`). One per user; `verified_at` stays NULL until the user
+-- confirms enrollment with a valid code. `secret` is the base32 shared secret,
+-- envelope-encrypted when `[features.field_encryption]` is configured.
+CREATE TABLE IF NOT EXISTS admin_totp_enrollments (
+    user_id UUID PRIMARY KEY NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    secret TEXT NOT NULL,
+    verified_at TIMESTAMPTZ,
+    -- Highest accepted time step; codes at or below it are rejected as replays
+    last_used_step BIGINT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- ─────────────────────────────────────────────────────────────────────────────
+-- admin_passkeys
+-- ─────────────────────────────────────────────────────────────────────────────
+-- WebAuthn credentials admins use to sign in or as a second factor
+-- (`[auth.admin]`). `credential_id` is the base64url credential ID sent by the
+-- authenticator; `credential` is the serialized public key and signature
+-- counter, opaque to everything but the WebAuthn layer.
+CREATE TABLE IF NOT EXISTS admin_passkeys (
+    id UUID PRIMARY KEY NOT NULL,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    credential_id TEXT NOT NULL UNIQUE,
+    name VARCHAR(64) NOT NULL,
+    credential TEXT NOT NULL,
+    last_used_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_admin_passkeys_user
+    ON admin_passkeys(user_id);
+
+-- ─────────────────────────────────────────────────────────────────────────────
+-- shadow_results
+-- ─────────────────────────────────────────────────────────────────────────────
+-- Requests mirrored by `[features.shadow_traffic]` rules. Each row pairs the
+-- response the client received (primary) with the one from the candidate
+-- provider/model (shadow). Outputs are truncated to the configured size.
+-- No FKs on org/project, matching usage_records.
+CREATE TABLE IF NOT EXISTS shadow_results (
+    id UUID PRIMARY KEY NOT NULL,
+    rule VARCHAR(64) NOT NULL,
+    org_id UUID,
+    project_id UUID,
+    primary_provider VARCHAR(64) NOT NULL,
+    primary_model VARCHAR(128) NOT NULL,
+    primary_status INTEGER NOT NULL,
+    primary_latency_ms BIGINT NOT NULL,
+    primary_input_tokens BIGINT,
+    primary_output_tokens BIGINT,
+    primary_cost_microcents BIGINT,
+    primary_output TEXT NOT NULL,
+    shadow_provider VARCHAR(64) NOT NULL,
+    shadow_model VARCHAR(128) NOT NULL,
+    -- NULL when the shadow call failed before a response
+    shadow_status INTEGER,
+    shadow_latency_ms BIGINT,
+    shadow_input_tokens BIGINT,
+    shadow_output_tokens BIGINT,
+    shadow_cost_microcents BIGINT,
+    shadow_output TEXT NOT NULL,
+    shadow_error TEXT,
+    -- Line similarity of the two outputs (0.0-1.0); NULL when either call failed
+    similarity DOUBLE PRECISION,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_shadow_results_rule_created
+    ON shadow_results(rule, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_shadow_results_created
+    ON shadow_results(created_at DESC);
+
+-- ─────────────────────────────────────────────────────────────────────────────
+-- usage_anomalies
+-- ─────────────────────────────────────────────────────────────────────────────
+-- Findings from `[features.anomaly_detection]`: an API key or organization
+-- whose recent usage departs from its baseline (spend spike, model mix shift,
+-- new referer). `fingerprint` identifies the finding (kind, subject and
+-- detail) so repeat detections within the cooldown are skipped.
+-- No FKs on org/api key, matching usage_records.
+CREATE TABLE IF NOT EXISTS usage_anomalies (
+    id UUID PRIMARY KEY NOT NULL,
+    kind VARCHAR(32) NOT NULL,
+    org_id UUID,
+    -- NULL for organization-wide findings
+    api_key_id UUID,
+    -- Model or referer the finding is about, when it's about one
+    detail TEXT,
+    summary TEXT NOT NULL,
+    observed DOUBLE PRECISION NOT NULL,
+    expected DOUBLE PRECISION NOT NULL,
+    score DOUBLE PRECISION NOT NULL,
+    window_start TIMESTAMPTZ NOT NULL,
+    window_end TIMESTAMPTZ NOT NULL,
+    fingerprint TEXT NOT NULL,
+    -- Set when a temporary rate limit was applied to the API key
+    rate_limited_until TIMESTAMPTZ,
+    acknowledged_at TIMESTAMPTZ,
+    acknowledged_by UUID,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_usage_anomalies_fingerprint
+    ON usage_anomalies(fingerprint, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_usage_anomalies_org_created
+    ON usage_anomalies(org_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_usage_anomalies_created
+    ON usage_anomalies(created_at DESC);
+
+-- Provider probe results: synthetic canary completions run by the provider
+-- health checker ([providers.<name>.health_check.probe]), kept as a latency
+-- and correctness history per provider and model.
+CREATE TABLE IF NOT EXISTS provider_probe_results (
+    id UUID PRIMARY KEY NOT NULL,
+    provider VARCHAR(255) NOT NULL,
+    model VARCHAR(255) NOT NULL,
+    -- Whether the provider returned a completion at all
+    success BOOLEAN NOT NULL,
+    -- Whether the completion contained the expected answer
+    correct BOOLEAN NOT NULL,
+    latency_ms BIGINT NOT NULL,
+    input_tokens BIGINT,
+    output_tokens BIGINT,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_provider_probe_results_provider_created
+    ON provider_probe_results(provider, created_at);
+
+-- Job leaders: the node that last ran each cluster-wide background job
+-- (see jobs::leader_lock). A row changes hands when another node wins the
+-- job's lock, e.g. after the previous leader stopped.
+CREATE TABLE IF NOT EXISTS job_leaders (
+    job VARCHAR(255) PRIMARY KEY NOT NULL,
+    node_id VARCHAR(255) NOT NULL,
+    -- When node_id took over the job
+    acquired_at TIMESTAMPTZ NOT NULL,
+    -- When node_id last ran the job
+    last_run_at TIMESTAMPTZ NOT NULL
+);
+
+-- Job runs: history of background job runs for the jobs admin API
+-- (see jobs::scheduler). Trimmed to the most recent runs per job.
+CREATE TABLE IF NOT EXISTS job_runs (
+    id UUID PRIMARY KEY NOT NULL,
+    job VARCHAR(255) NOT NULL,
+    node_id VARCHAR(255) NOT NULL,
+    -- 'scheduled' or 'manual'
+    triggered_by VARCHAR(32) NOT NULL,
+    -- 'succeeded' or 'failed'
+    status VARCHAR(32) NOT NULL,
+    summary TEXT,
+    error TEXT,
+    started_at TIMESTAMPTZ NOT NULL,
+    finished_at TIMESTAMPTZ NOT NULL,
+    duration_ms BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_job_runs_job_started ON job_runs(job, started_at);
+
+-- Paused jobs: scheduled runs of these jobs are skipped on every node until
+-- they are resumed. Manual runs still go ahead.
+CREATE TABLE IF NOT EXISTS paused_jobs (
+    job VARCHAR(255) PRIMARY KEY NOT NULL,
+    paused_at TIMESTAMPTZ NOT NULL
+);
+
+-- Provider settings: runtime overrides for providers defined in the config
+-- file, set through the admin API. NULL columns keep the file's value.
+CREATE TABLE IF NOT EXISTS provider_settings (
+    id UUID PRIMARY KEY NOT NULL,
+    provider_name VARCHAR(255) NOT NULL UNIQUE,
+    enabled BOOLEAN,
+    -- Circuit breaker fields to override
+    circuit_breaker JSONB,
+    -- Retry fields to override
+    retry JSONB,
+    max_concurrent INTEGER,
+    updated_by UUID,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL
+);
+
+-- Idempotency keys: claims on client-supplied Idempotency-Key headers for
+-- completion endpoints, and the responses replayed to repeats of them.
+-- key_hash covers the caller, endpoint and key.
+CREATE TABLE IF NOT EXISTS idempotency_keys (
+    key_hash VARCHAR(64) PRIMARY KEY NOT NULL,
+    request_hash VARCHAR(64) NOT NULL,
+    -- 'in_progress' or 'completed'
+    status VARCHAR(16) NOT NULL CHECK (status IN ('in_progress', 'completed')),
+    -- NULL while in progress, or when the response was too large to store
+    response_status INTEGER,
+    response_content_type VARCHAR(255),
+    response_body BYTEA,
+    created_at TIMESTAMPTZ NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_idempotency_keys_expires_at ON idempotency_keys(expires_at);
+
+-- Provider usage imports: usage and billing exports pulled from provider usage
+-- APIs or uploaded as CSV, for reconciling against usage_records. An import
+-- replaces all earlier imported rows for its provider and dates.
+CREATE TABLE IF NOT EXISTS provider_usage_imports (
+    id UUID PRIMARY KEY NOT NULL,
+    provider VARCHAR(64) NOT NULL,
+    -- 'openai', 'anthropic' or 'azure'
+    source VARCHAR(16) NOT NULL CHECK (source IN ('openai', 'anthropic', 'azure')),
+    -- 'api' or 'csv'
+    method VARCHAR(8) NOT NULL CHECK (method IN ('api', 'csv')),
+    start_date DATE NOT NULL,
+    end_date DATE NOT NULL,
+    row_count INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_provider_usage_imports_provider
+    ON provider_usage_imports(provider, created_at DESC);
+
+-- Daily per-model usage as reported by the provider
+CREATE TABLE IF NOT EXISTS provider_usage (
+    provider VARCHAR(64) NOT NULL,
+    date DATE NOT NULL,
+    model VARCHAR(255) NOT NULL,
+    import_id UUID NOT NULL REFERENCES provider_usage_imports(id) ON DELETE CASCADE,
+    -- NULL when the source doesn't report it
+    request_count BIGINT,
+    input_tokens BIGINT NOT NULL DEFAULT 0,
+    output_tokens BIGINT NOT NULL DEFAULT 0,
+    cost_microcents BIGINT,
+    PRIMARY KEY (provider, date, model)
+);
+
+CREATE INDEX IF NOT EXISTS idx_provider_usage_date ON provider_usage(date);
+CREATE INDEX IF NOT EXISTS idx_provider_usage_import ON provider_usage(import_id);
+
+-- Model catalog entries added through the admin API, for models the
+-- models.dev catalog doesn't know (self-hosted, fine-tuned) or describes
+-- wrongly. They take precedence over models.dev data.
+CREATE TABLE IF NOT EXISTS model_catalog_entries (
+    id UUID PRIMARY KEY NOT NULL,
+    -- Catalog provider ID
+    provider VARCHAR(128) NOT NULL,
+    model VARCHAR(255) NOT NULL,
+    -- Capabilities, limits, modalities, pricing and dates
+    metadata JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL,
+    UNIQUE (provider, model)
+);
+
+-- Fine-tuning jobs started through /v1/fine_tuning/jobs, recording who owns
+-- each provider job so it can be listed per org/project and billed once.
+CREATE TABLE IF NOT EXISTS fine_tuning_jobs (
+    id UUID PRIMARY KEY NOT NULL,
+    provider VARCHAR(64) NOT NULL,
+    provider_job_id VARCHAR(255) NOT NULL,
+    org_id UUID NOT NULL,
+    project_id UUID,
+    team_id UUID,
+    user_id UUID,
+    api_key_id UUID,
+    service_account_id UUID,
+    -- Base model as sent to the provider
+    model VARCHAR(255) NOT NULL,
+    -- Training and validation files as the client gave them
+    training_file VARCHAR(255) NOT NULL,
+    validation_file VARCHAR(255),
+    -- Last status reported by the provider
+    status VARCHAR(32) NOT NULL,
+    fine_tuned_model VARCHAR(255),
+    trained_tokens BIGINT,
+    -- Set once the finished job's usage is recorded and its model registered
+    completed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_fine_tuning_jobs_org_created
+    ON fine_tuning_jobs(org_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_fine_tuning_jobs_incomplete
+    ON fine_tuning_jobs(updated_at) WHERE completed_at IS NULL;
+
+-- Copies of gateway files uploaded to providers, so a file is uploaded to each
+-- provider once and the copies are deleted along with it.
+CREATE TABLE IF NOT EXISTS provider_files (
+    id UUID PRIMARY KEY NOT NULL,
+    file_id UUID NOT NULL,
+    provider VARCHAR(64) NOT NULL,
+    provider_file_id VARCHAR(255) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    UNIQUE (file_id, provider)
+);
+
+-- Agents: a model with a system prompt and server-executed tools, defined by
+-- admins and run through /v1/agents/{agent_id}/runs
+CREATE TABLE IF NOT EXISTS agents (
+    id UUID PRIMARY KEY NOT NULL,
+    org_id UUID NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    description TEXT,
+    model VARCHAR(255) NOT NULL,
+    instructions TEXT,
+    -- Responses API tool definitions
+    tools JSONB NOT NULL DEFAULT '[]',
+    -- Vector store IDs searched with file_search
+    vector_store_ids JSONB NOT NULL DEFAULT '[]',
+    max_iterations INTEGER,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL,
+    UNIQUE (org_id, name)
+);
+
+-- Runs of an agent, with the caller and usage they are attributed to
+CREATE TABLE IF NOT EXISTS agent_runs (
+    id UUID PRIMARY KEY NOT NULL,
+    agent_id UUID NOT NULL REFERENCES agents(id) ON DELETE CASCADE,
+    org_id UUID NOT NULL,
+    project_id UUID,
+    user_id UUID,
+    api_key_id UUID,
+    service_account_id UUID,
+    provider VARCHAR(64) NOT NULL,
+    model VARCHAR(255) NOT NULL,
+    status VARCHAR(32) NOT NULL,
+    -- Input the run was started with
+    input JSONB NOT NULL,
+    output_text TEXT,
+    error TEXT,
+    input_tokens BIGINT NOT NULL DEFAULT 0,
+    output_tokens BIGINT NOT NULL DEFAULT 0,
+    cost_microcents BIGINT,
+    created_at TIMESTAMPTZ NOT NULL,
+    completed_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_agent_runs_agent_created
+    ON agent_runs(agent_id, created_at DESC);
+
+-- Output items of a run (messages, tool calls and their outputs), in order
+CREATE TABLE IF NOT EXISTS agent_run_steps (
+    id UUID PRIMARY KEY NOT NULL,
+    run_id UUID NOT NULL REFERENCES agent_runs(id) ON DELETE CASCADE,
+    sequence INTEGER NOT NULL,
+    step_type VARCHAR(64) NOT NULL,
+    -- Responses API output item
+    content JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    UNIQUE (run_id, sequence)
+);
+
+-- Scheduled tasks: a prompt run on a cron schedule, with the output appended
+-- to a conversation or sent to a webhook
+CREATE TABLE IF NOT EXISTS scheduled_tasks (
+    id UUID PRIMARY KEY NOT NULL,
+    org_id UUID NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    description TEXT,
+    -- Five-field cron expression, evaluated in UTC
+    schedule VARCHAR(255) NOT NULL,
+    model VARCHAR(255) NOT NULL,
+    instructions TEXT,
+    prompt TEXT NOT NULL,
+    -- 'conversation' or 'webhook'
+    delivery_type VARCHAR(32) NOT NULL,
+    conversation_id UUID,
+    webhook_url TEXT,
+    webhook_secret TEXT,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    next_run_at TIMESTAMPTZ,
+    last_run_at TIMESTAMPTZ,
+    consecutive_failures INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL,
+    UNIQUE (org_id, name)
+);
+
+CREATE INDEX IF NOT EXISTS idx_scheduled_tasks_due
+    ON scheduled_tasks(next_run_at) WHERE enabled;
+
+-- Run history of scheduled tasks, pruned to the newest runs per task
+CREATE TABLE IF NOT EXISTS scheduled_task_runs (
+    id UUID PRIMARY KEY NOT NULL,
+    task_id UUID NOT NULL REFERENCES scheduled_tasks(id) ON DELETE CASCADE,
+    status VARCHAR(32) NOT NULL,
+    output_text TEXT,
+    error TEXT,
+    input_tokens BIGINT NOT NULL DEFAULT 0,
+    output_tokens BIGINT NOT NULL DEFAULT 0,
+    cost_microcents BIGINT,
+    started_at TIMESTAMPTZ NOT NULL,
+    finished_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_scheduled_task_runs_task_started
+    ON scheduled_task_runs(task_id, started_at DESC);
+
+-- Invitations: an email address invited to join an organization (and
+-- optionally one of its teams). Only a SHA-256 hash of the invite token is
+-- stored. Expiry is derived from `expires_at`; `status` only records whether
+-- the invite was accepted or revoked.
+CREATE TABLE IF NOT EXISTS invitations (
+    id UUID PRIMARY KEY NOT NULL,
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    team_id UUID REFERENCES teams(id) ON DELETE CASCADE,
+    -- Lowercased
+    email VARCHAR(255) NOT NULL,
+    role VARCHAR(64) NOT NULL,
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    status VARCHAR(32) NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'accepted', 'revoked')),
+    invited_by UUID,
+    expires_at TIMESTAMPTZ NOT NULL,
+    send_count INTEGER NOT NULL DEFAULT 0,
+    last_sent_at TIMESTAMPTZ,
+    accepted_at TIMESTAMPTZ,
+    accepted_by UUID,
+    revoked_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_invitations_org_created
+    ON invitations(org_id, created_at DESC);
+
+CREATE INDEX IF NOT EXISTS idx_invitations_pending_email
+    ON invitations(email) WHERE status = 'pending';
+
+-- Notification email send log, for troubleshooting delivery. Bodies aren't
+-- stored. Pruned by the retention worker.
+CREATE TABLE IF NOT EXISTS email_send_log (
+    id UUID PRIMARY KEY NOT NULL,
+    org_id UUID REFERENCES organizations(id) ON DELETE CASCADE,
+    kind VARCHAR(64) NOT NULL,
+    from_address VARCHAR(320) NOT NULL,
+    recipients JSONB NOT NULL,
+    subject TEXT NOT NULL,
+    status VARCHAR(32) NOT NULL CHECK (status IN ('sent', 'failed', 'rate_limited')),
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_email_send_log_created
+    ON email_send_log(created_at DESC);
+
+CREATE INDEX IF NOT EXISTS idx_email_send_log_org_created
+    ON email_send_log(org_id, created_at DESC);
+
+-- Role elevations: time-boxed break-glass changes to a member's organization
+-- role. `previous_role` is captured on approval and restored when the
+-- elevation is revoked or expires. A member has at most one open request.
+CREATE TABLE IF NOT EXISTS role_elevations (
+    id UUID PRIMARY KEY NOT NULL,
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    role VARCHAR(64) NOT NULL,
+    previous_role VARCHAR(64),
+    justification TEXT NOT NULL,
+    duration_hours INTEGER NOT NULL,
+    status VARCHAR(32) NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'active', 'denied', 'revoked', 'expired')),
+    requested_by UUID,
+    decided_by UUID,
+    decision_note TEXT,
+    decided_at TIMESTAMPTZ,
+    expires_at TIMESTAMPTZ,
+    ended_at TIMESTAMPTZ,
+    ended_by UUID,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_role_elevations_org_created
+    ON role_elevations(org_id, created_at DESC);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_role_elevations_open
+    ON role_elevations(org_id, user_id) WHERE status IN ('pending', 'active');
+
+CREATE INDEX IF NOT EXISTS idx_role_elevations_active_expires
+    ON role_elevations(expires_at) WHERE status = 'active';
+
+-- Custom HTTP tools registered per organization through the admin API.
+-- Requests opt in by declaring a function tool with the same name.
+CREATE TABLE IF NOT EXISTS http_tools (
+    id UUID PRIMARY KEY NOT NULL,
+    org_id UUID NOT NULL,
+    name VARCHAR(64) NOT NULL,
+    description TEXT,
+    url TEXT NOT NULL,
+    -- Request headers; values are encrypted when field encryption is configured
+    headers JSONB NOT NULL DEFAULT '{}',
+    -- JSON Schema for the call arguments
+    parameters JSONB,
+    timeout_secs INTEGER NOT NULL,
+    max_response_bytes INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL,
+    UNIQUE (org_id, name)
+);
@@ -8,6 +8,24 @@ CREATE TABLE IF NOT EXISTS organizations (
     id TEXT PRIMARY KEY NOT NULL,
     slug TEXT NOT NULL UNIQUE,
     name TEXT NOT NULL,
+    -- Data residency policy (JSON: {"allowed_regions": [...]}), NULL = unrestricted
+    data_residency TEXT,
+    -- Default model/temperature/system prompt applied when clients omit them (JSON), NULL = none
+    request_defaults TEXT,
+    -- Conversation title/summary generation settings (JSON: {"enabled": ..., "model": ...}), NULL = gateway default
+    conversation_summaries TEXT,
+    -- Network ranges the org's API keys may be used from (JSON: {"ip_allowlist": [...]}), NULL = unrestricted
+    network_policy TEXT,
+    -- Parameter bounds for the org's requests (JSON: {"rules": [...]}), NULL = ungoverned
+    parameter_governance TEXT,
+    -- Model/provider allow and deny lists (JSON: {"allowed_models": [...], ...}), NULL = unrestricted
+    model_access TEXT,
+    -- Cheaper models to downgrade to under pressure (JSON: {"ladders": [...]}), NULL = never downgrade
+    model_degradation TEXT,
+    -- Plan and per-feature overrides (JSON: {"plan": "team", "file_search": true, ...}), NULL = default plan
+    entitlements TEXT,
+    -- Free-form labels for grouping and list filtering (JSON object: {"env": "prod"})
+    labels TEXT NOT NULL DEFAULT '{}',
     created_at TEXT NOT NULL DEFAULT (datetime('now')),
     updated_at TEXT NOT NULL DEFAULT (datetime('now')),
     deleted_at TEXT
@@ -50,6 +68,14 @@ CREATE TABLE IF NOT EXISTS projects (
     team_id TEXT REFERENCES teams(id) ON DELETE SET NULL,
     slug TEXT NOT NULL,
     name TEXT NOT NULL,
+    -- Default model/temperature/system prompt overriding the org defaults (JSON), NULL = inherit
+    request_defaults TEXT,
+    -- Parameter bounds applied after the org's (JSON: {"rules": [...]}), NULL = org rules only
+    parameter_governance TEXT,
+    -- Model/provider allow and deny lists checked with the org's (JSON), NULL = org policy only
+    model_access TEXT,
+    -- Free-form labels for grouping and list filtering (JSON object: {"env": "prod"})
+    labels TEXT NOT NULL DEFAULT '{}',
     created_at TEXT NOT NULL DEFAULT (datetime('now')),
     updated_at TEXT NOT NULL DEFAULT (datetime('now')),
     deleted_at TEXT,
@@ -264,6 +290,8 @@ CREATE TABLE IF NOT EXISTS org_sso_configs (
     allowed_email_domains TEXT,
     sync_attributes_on_login INTEGER NOT NULL DEFAULT 0,
     sync_memberships_on_login INTEGER NOT NULL DEFAULT 1,
+    -- JSON array of claim-based role rules evaluated during JIT provisioning
+    role_rules TEXT,
 
     -- ==========================================================================
     -- Status & Enforcement
@@ -493,6 +521,35 @@ CREATE INDEX IF NOT EXISTS idx_org_rbac_policy_versions_latest ON org_rbac_polic
 -- Index for cleanup jobs finding old versions by creation date
 CREATE INDEX IF NOT EXISTS idx_org_rbac_policy_versions_cleanup ON org_rbac_policy_versions(policy_id, created_at);
 
+-- ======================================================================
+-- Organization Request Policies
+-- ======================================================================
+
+-- Per-organization CEL policies evaluated against data-plane requests.
+-- action: 'allow' stops evaluation, 'deny' rejects the request, 'modify'
+-- overwrites top-level request fields with `modifications` and continues.
+CREATE TABLE IF NOT EXISTS org_request_policies (
+    id TEXT PRIMARY KEY NOT NULL,
+    org_id TEXT NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    description TEXT,
+    -- CEL expression over `request`, `subject` and `time`
+    condition TEXT NOT NULL,
+    action TEXT NOT NULL DEFAULT 'deny' CHECK (action IN ('allow', 'deny', 'modify')),
+    -- JSON object of request fields to set (modify only)
+    modifications TEXT,
+    -- Message returned to the caller when the policy denies a request
+    message TEXT,
+    -- Higher priority = evaluated first (descending order)
+    priority INTEGER NOT NULL DEFAULT 0,
+    enabled INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE(org_id, name)
+);
+
+CREATE INDEX IF NOT EXISTS idx_org_request_policies_org_priority ON org_request_policies(org_id, priority DESC);
+
 -- ======================================================================
 -- API Keys
 -- ======================================================================
@@ -509,6 +566,10 @@ CREATE TABLE IF NOT EXISTS api_keys (
     name TEXT NOT NULL,
     key_hash TEXT NOT NULL UNIQUE,
     key_prefix TEXT NOT NULL,
+    -- Hash scheme that produced key_hash; see [auth.api_key]
+    hash_scheme TEXT NOT NULL DEFAULT 'sha256' CHECK (hash_scheme IN ('sha256', 'hmac_sha256', 'argon2')),
+    -- Salted Argon2id PHC string checked after lookup (argon2 scheme only)
+    key_verifier TEXT,
     -- Budget enforcement
     budget_amount INTEGER,
     budget_period TEXT CHECK (budget_period IN ('daily', 'monthly')),
@@ -528,6 +589,8 @@ CREATE TABLE IF NOT EXISTS api_keys (
     expires_at TEXT,
     last_used_at TEXT,
     rotation_grace_until TEXT,
+    -- Free-form labels for grouping and list filtering (JSON object: {"env": "prod"})
+    labels TEXT NOT NULL DEFAULT '{}',
     created_at TEXT NOT NULL DEFAULT (datetime('now')),
     updated_at TEXT NOT NULL DEFAULT (datetime('now'))
 );
@@ -563,6 +626,8 @@ CREATE TABLE IF NOT EXISTS dynamic_providers (
     -- Sovereignty metadata (data residency, compliance requirements)
     sovereignty TEXT,
     is_enabled INTEGER NOT NULL DEFAULT 1,
+    -- Free-form labels for grouping and list filtering (JSON object: {"env": "prod"})
+    labels TEXT NOT NULL DEFAULT '{}',
     created_at TEXT NOT NULL DEFAULT (datetime('now')),
     updated_at TEXT NOT NULL DEFAULT (datetime('now')),
     UNIQUE(owner_type, owner_id, name)
@@ -624,7 +689,38 @@ CREATE TABLE IF NOT EXISTS usage_records (
     -- Shell process exit code (only populated for shell tool records).
     -- Kept separate from status_code (HTTP) so a shell that exits non-zero
     -- inside a 200 response is observable in usage queries.
-    tool_exit_code INTEGER
+    tool_exit_code INTEGER,
+    -- Subject (sub claim) of an externally-issued JWT used to authenticate the request
+    jwt_subject TEXT,
+    -- Error category from the gateway's error taxonomy (rate_limited,
+    -- context_length, ...) for failed requests; NULL on success
+    error_code TEXT,
+    -- Re-prompts made because a json_schema response failed validation;
+    -- NULL when the response wasn't validated
+    structured_output_repairs INTEGER,
+    -- Estimated prompt tokens before and after context compression; NULL
+    -- when the request wasn't compressed
+    context_original_tokens INTEGER,
+    context_compressed_tokens INTEGER,
+    -- Model the client requested when the request was downgraded along an
+    -- org's degradation ladder, and why (rate_limited, over_budget,
+    -- circuit_open); NULL when it wasn't downgraded
+    degraded_from_model TEXT,
+    degradation_reason TEXT,
+    -- Streaming timings: time to first token, mean gap between chunks and
+    -- output tokens per second of generation; NULL for non-streaming requests
+    ttft_ms INTEGER,
+    inter_token_latency_ms REAL,
+    output_tokens_per_second REAL,
+    -- Idempotency-Key header the client sent, if any
+    idempotency_key TEXT,
+    -- Requests sent to the provider counting retries, and how many of them
+    -- timed out before a retry (possibly billed upstream as well)
+    provider_attempts INTEGER,
+    suspected_duplicate_attempts INTEGER,
+    -- Cost allocation tags from the X-Hadrian-Tags header
+    -- (JSON: {"feature": "autocomplete", ...}), NULL when untagged
+    tags TEXT
 );
 
 -- SQLite doesn't support partial indexes; use regular indexes
@@ -640,6 +736,7 @@ CREATE INDEX IF NOT EXISTS idx_usage_records_recorded_at ON usage_records(record
 CREATE INDEX IF NOT EXISTS idx_usage_records_recorded_at_id ON usage_records(recorded_at DESC, id DESC);
 CREATE INDEX IF NOT EXISTS idx_usage_records_model ON usage_records(model);
 CREATE INDEX IF NOT EXISTS idx_usage_records_request_id ON usage_records(request_id);
+CREATE INDEX IF NOT EXISTS idx_usage_records_error_code ON usage_records(error_code, recorded_at);
 
 -- ======================================================================
 -- Model Pricing
@@ -723,6 +820,10 @@ CREATE TABLE IF NOT EXISTS conversations (
     -- Message history (JSON array)
     messages TEXT NOT NULL DEFAULT '[]',
     pin_order INTEGER,
+    -- Rolling summary generated by the conversation_summaries job
+    summary TEXT,
+    -- Number of messages covered by the summary
+    summary_message_count INTEGER NOT NULL DEFAULT 0,
     created_at TEXT NOT NULL DEFAULT (datetime('now')),
     updated_at TEXT NOT NULL DEFAULT (datetime('now')),
     deleted_at TEXT
@@ -735,6 +836,27 @@ CREATE INDEX IF NOT EXISTS idx_conversations_owner_active ON conversations(owner
 -- Index for pinned conversations (for efficient pinned queries per owner)
 CREATE INDEX IF NOT EXISTS idx_conversations_owner_pinned ON conversations(owner_type, owner_id, pin_order) WHERE pin_order IS NOT NULL AND deleted_at IS NULL;
 
+-- Conversation branches: alternate message histories created by editing or
+-- deleting a message. The root branch is created lazily on the first fork.
+-- conversations.messages always mirrors the active branch so readers that
+-- are unaware of branching keep working.
+-- fork_index: number of messages inherited from the parent branch
+CREATE TABLE IF NOT EXISTS conversation_branches (
+    id TEXT PRIMARY KEY NOT NULL,
+    conversation_id TEXT NOT NULL REFERENCES conversations(id) ON DELETE CASCADE,
+    parent_branch_id TEXT REFERENCES conversation_branches(id) ON DELETE SET NULL,
+    fork_index INTEGER NOT NULL DEFAULT 0,
+    -- Message history for this branch (JSON array)
+    messages TEXT NOT NULL DEFAULT '[]',
+    is_active INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_conversation_branches_conversation ON conversation_branches(conversation_id, created_at);
+-- At most one active branch per conversation
+CREATE UNIQUE INDEX IF NOT EXISTS idx_conversation_branches_active ON conversation_branches(conversation_id) WHERE is_active = 1;
+
 -- ======================================================================
 -- Audit Logs
 -- ======================================================================
@@ -838,6 +960,8 @@ CREATE TABLE IF NOT EXISTS vector_stores (
     metadata TEXT,
     -- Expiration policy: {"anchor": "last_active_at", "days": N}
     expires_after TEXT,
+    -- Default hybrid search fusion weights: {"embedding_weight": 1.0, "text_weight": 1.0}
+    hybrid_search TEXT,
     expires_at TEXT,
     last_active_at TEXT,
     created_at TEXT NOT NULL DEFAULT (datetime('now')),
@@ -913,6 +1037,8 @@ CREATE TABLE IF NOT EXISTS templates (
     content TEXT NOT NULL,
     -- Optional metadata (temperature, max_tokens, etc.)
     metadata TEXT,
+    -- Free-form labels for grouping and list filtering (JSON object: {"env": "prod"})
+    labels TEXT NOT NULL DEFAULT '{}',
     created_at TEXT NOT NULL DEFAULT (datetime('now')),
     updated_at TEXT NOT NULL DEFAULT (datetime('now')),
     deleted_at TEXT,
@@ -953,6 +1079,24 @@ CREATE INDEX IF NOT EXISTS idx_service_accounts_slug ON service_accounts(slug);
 CREATE INDEX IF NOT EXISTS idx_service_accounts_org_active ON service_accounts(org_id) WHERE deleted_at IS NULL;
 CREATE INDEX IF NOT EXISTS idx_service_accounts_org_slug_active ON service_accounts(org_id, slug) WHERE deleted_at IS NULL;
 
+-- Client certificate → service account mappings for mTLS authentication.
+-- `match_value` is stored normalized (see CertMatchType::normalize) and is
+-- globally unique per match type so a certificate resolves to at most one
+-- service account per identifier.
+CREATE TABLE IF NOT EXISTS client_cert_mappings (
+    id TEXT PRIMARY KEY NOT NULL,
+    org_id TEXT NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    service_account_id TEXT NOT NULL REFERENCES service_accounts(id) ON DELETE CASCADE,
+    match_type TEXT NOT NULL CHECK (match_type IN ('fingerprint_sha256', 'san_uri', 'san_dns', 'san_email', 'subject_cn')),
+    match_value TEXT NOT NULL,
+    description TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE(match_type, match_value)
+);
+
+CREATE INDEX IF NOT EXISTS idx_client_cert_mappings_service_account
+    ON client_cert_mappings(service_account_id, created_at DESC);
+
 -- ======================================================================
 -- Skills
 -- ======================================================================
@@ -1258,3 +1402,615 @@ CREATE INDEX IF NOT EXISTS idx_mcp_pending_approvals_response
     ON mcp_pending_approvals(response_id);
 CREATE INDEX IF NOT EXISTS idx_mcp_pending_approvals_expires
     ON mcp_pending_approvals(expires_at);
+
+-- ─────────────────────────────────────────────────────────────────────────────
+-- scheduled_report_runs
+-- ─────────────────────────────────────────────────────────────────────────────
+-- One row per attempt to generate and deliver a scheduled report
+-- (`[features.scheduled_reports]`) for an org and reporting period.
+-- Report definitions live in config, so `report_name` is not a foreign key.
+CREATE TABLE IF NOT EXISTS scheduled_report_runs (
+    id TEXT PRIMARY KEY NOT NULL,
+    report_name TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    org_id TEXT NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    -- Inclusive reporting period (YYYY-MM-DD)
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    format TEXT NOT NULL,
+    delivery TEXT NOT NULL,
+    status TEXT NOT NULL CHECK (status IN ('delivered', 'failed')),
+    error TEXT,
+    -- Rendered report body, kept so admins can re-download past reports
+    content TEXT,
+    created_at TEXT NOT NULL
+);
+
+-- The scheduler looks up prior attempts by (report, org, period) every tick
+CREATE INDEX IF NOT EXISTS idx_scheduled_report_runs_period
+    ON scheduled_report_runs(report_name, org_id, period_start);
+CREATE INDEX IF NOT EXISTS idx_scheduled_report_runs_org_created
+    ON scheduled_report_runs(org_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_scheduled_report_runs_created
+    ON scheduled_report_runs(created_at DESC);
+
+-- ─────────────────────────────────────────────────────────────────────────────
+-- vector_store_syncs / vector_store_sync_items
+-- ─────────────────────────────────────────────────────────────────────────────
+-- One row per run of a `[features.vector_store_sync]` source. Sources live in
+-- config, so `source_name` is not a foreign key.
+CREATE TABLE IF NOT EXISTS vector_store_syncs (
+    id TEXT PRIMARY KEY NOT NULL,
+    vector_store_id TEXT NOT NULL REFERENCES vector_stores(id) ON DELETE CASCADE,
+    source_name TEXT NOT NULL,
+    connector TEXT NOT NULL,
+    status TEXT NOT NULL CHECK (status IN ('running', 'succeeded', 'partially_succeeded', 'failed')),
+    files_added INTEGER NOT NULL DEFAULT 0,
+    files_updated INTEGER NOT NULL DEFAULT 0,
+    files_deleted INTEGER NOT NULL DEFAULT 0,
+    files_unchanged INTEGER NOT NULL DEFAULT 0,
+    files_failed INTEGER NOT NULL DEFAULT 0,
+    error TEXT,
+    started_at TEXT NOT NULL,
+    finished_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_vector_store_syncs_store_started
+    ON vector_store_syncs(vector_store_id, started_at DESC);
+-- The scheduler looks up the latest run per source every tick
+CREATE INDEX IF NOT EXISTS idx_vector_store_syncs_source_started
+    ON vector_store_syncs(vector_store_id, source_name, started_at DESC);
+
+-- The version of each source document last ingested, so unchanged documents
+-- are skipped and removed ones can be deleted from the store.
+CREATE TABLE IF NOT EXISTS vector_store_sync_items (
+    vector_store_id TEXT NOT NULL REFERENCES vector_stores(id) ON DELETE CASCADE,
+    source_name TEXT NOT NULL,
+    source_key TEXT NOT NULL,
+    version TEXT NOT NULL,
+    file_id TEXT NOT NULL,
+    synced_at TEXT NOT NULL,
+    PRIMARY KEY (vector_store_id, source_name, source_key)
+);
+
+-- ─────────────────────────────────────────────────────────────────────────────
+-- federated_gateways / federated_usage
+-- ─────────────────────────────────────────────────────────────────────────────
+-- Written only on a federation hub (`[federation.hub]`). Each satellite gateway
+-- periodically pushes its daily usage totals and provider health; the hub
+-- keeps the latest snapshot per satellite. Satellites are configured in TOML,
+-- so `id` is the satellite's configured ID rather than a generated UUID.
+CREATE TABLE IF NOT EXISTS federated_gateways (
+    id TEXT PRIMARY KEY NOT NULL,
+    region TEXT,
+    version TEXT NOT NULL,
+    -- JSON array of provider health snapshots from the latest report
+    providers TEXT NOT NULL DEFAULT '[]',
+    first_report_at TEXT NOT NULL,
+    last_report_at TEXT NOT NULL
+);
+
+-- Daily per-model totals as reported by each satellite. Satellites resend
+-- whole days, so rows are replaced rather than accumulated.
+CREATE TABLE IF NOT EXISTS federated_usage (
+    gateway_id TEXT NOT NULL REFERENCES federated_gateways(id) ON DELETE CASCADE,
+    date TEXT NOT NULL,
+    model TEXT NOT NULL,
+    total_cost_microcents INTEGER NOT NULL DEFAULT 0,
+    input_tokens INTEGER NOT NULL DEFAULT 0,
+    output_tokens INTEGER NOT NULL DEFAULT 0,
+    total_tokens INTEGER NOT NULL DEFAULT 0,
+    request_count INTEGER NOT NULL DEFAULT 0,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (gateway_id, date, model)
+);
+
+CREATE INDEX IF NOT EXISTS idx_federated_usage_date
+    ON federated_usage(date);
+
+-- ─────────────────────────────────────────────────────────────────────────────
+-- admin_totp_enrollments
+-- ─────────────────────────────────────────────────────────────────────────────
+-- TOTP authenticators used for admin step-up authentication
+-- (`[auth.step_up]`). One per user; `verified_at` stays NULL until the user
+-- confirms enrollment with a valid code. `secret` is the base32 shared secret,
+-- envelope-encrypted when `[features.field_encryption]` is configured.
+CREATE TABLE IF NOT EXISTS admin_totp_enrollments (
+    user_id TEXT PRIMARY KEY NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    secret TEXT NOT NULL,
+    verified_at TEXT,
+    -- Highest accepted time step; codes at or below it are rejected as replays
+    last_used_step INTEGER,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+-- ─────────────────────────────────────────────────────────────────────────────
+-- admin_passkeys
+-- ─────────────────────────────────────────────────────────────────────────────
+-- WebAuthn credentials admins use to sign in or as a second factor
+-- (`[auth.admin]`). `credential_id` is the base64url credential ID sent by the
+-- authenticator; `credential` is the serialized public key and signature
+-- counter, opaque to everything but the WebAuthn layer.
+CREATE TABLE IF NOT EXISTS admin_passkeys (
+    id TEXT PRIMARY KEY NOT NULL,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    credential_id TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    credential TEXT NOT NULL,
+    last_used_at TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_admin_passkeys_user
+    ON admin_passkeys(user_id);
+
+-- ─────────────────────────────────────────────────────────────────────────────
+-- shadow_results
+-- ─────────────────────────────────────────────────────────────────────────────
+-- Requests mirrored by `[features.shadow_traffic]` rules. Each row pairs the
+-- response the client received (primary) with the one from the candidate
+-- provider/model (shadow). Outputs are truncated to the configured size.
+-- No FKs on org/project, matching usage_records.
+CREATE TABLE IF NOT EXISTS shadow_results (
+    id TEXT PRIMARY KEY NOT NULL,
+    rule TEXT NOT NULL,
+    org_id TEXT,
+    project_id TEXT,
+    primary_provider TEXT NOT NULL,
+    primary_model TEXT NOT NULL,
+    primary_status INTEGER NOT NULL,
+    primary_latency_ms INTEGER NOT NULL,
+    primary_input_tokens INTEGER,
+    primary_output_tokens INTEGER,
+    primary_cost_microcents INTEGER,
+    primary_output TEXT NOT NULL,
+    shadow_provider TEXT NOT NULL,
+    shadow_model TEXT NOT NULL,
+    -- NULL when the shadow call failed before a response
+    shadow_status INTEGER,
+    shadow_latency_ms INTEGER,
+    shadow_input_tokens INTEGER,
+    shadow_output_tokens INTEGER,
+    shadow_cost_microcents INTEGER,
+    shadow_output TEXT NOT NULL,
+    shadow_error TEXT,
+    -- Line similarity of the two outputs (0.0-1.0); NULL when either call failed
+    similarity REAL,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_shadow_results_rule_created
+    ON shadow_results(rule, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_shadow_results_created
+    ON shadow_results(created_at DESC);
+
+-- ─────────────────────────────────────────────────────────────────────────────
+-- usage_anomalies
+-- ─────────────────────────────────────────────────────────────────────────────
+-- Findings from `[features.anomaly_detection]`: an API key or organization
+-- whose recent usage departs from its baseline (spend spike, model mix shift,
+-- new referer). `fingerprint` identifies the finding (kind, subject and
+-- detail) so repeat detections within the cooldown are skipped.
+-- No FKs on org/api key, matching usage_records.
+CREATE TABLE IF NOT EXISTS usage_anomalies (
+    id TEXT PRIMARY KEY NOT NULL,
+    kind TEXT NOT NULL,
+    org_id TEXT,
+    -- NULL for organization-wide findings
+    api_key_id TEXT,
+    -- Model or referer the finding is about, when it's about one
+    detail TEXT,
+    summary TEXT NOT NULL,
+    observed REAL NOT NULL,
+    expected REAL NOT NULL,
+    score REAL NOT NULL,
+    window_start TEXT NOT NULL,
+    window_end TEXT NOT NULL,
+    fingerprint TEXT NOT NULL,
+    -- Set when a temporary rate limit was applied to the API key
+    rate_limited_until TEXT,
+    acknowledged_at TEXT,
+    acknowledged_by TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_usage_anomalies_fingerprint
+    ON usage_anomalies(fingerprint, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_usage_anomalies_org_created
+    ON usage_anomalies(org_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_usage_anomalies_created
+    ON usage_anomalies(created_at DESC);
+
+-- Provider probe results: synthetic canary completions run by the provider
+-- health checker ([providers.<name>.health_check.probe]), kept as a latency
+-- and correctness history per provider and model.
+CREATE TABLE IF NOT EXISTS provider_probe_results (
+    id TEXT PRIMARY KEY NOT NULL,
+    provider TEXT NOT NULL,
+    model TEXT NOT NULL,
+    -- Whether the provider returned a completion at all
+    success INTEGER NOT NULL,
+    -- Whether the completion contained the expected answer
+    correct INTEGER NOT NULL,
+    latency_ms INTEGER NOT NULL,
+    input_tokens INTEGER,
+    output_tokens INTEGER,
+    error TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_provider_probe_results_provider_created
+    ON provider_probe_results(provider, created_at);
+
+-- Job leaders: the node that last ran each cluster-wide background job
+-- (see jobs::leader_lock). A row changes hands when another node wins the
+-- job's lock, e.g. after the previous leader stopped.
+CREATE TABLE IF NOT EXISTS job_leaders (
+    job TEXT PRIMARY KEY NOT NULL,
+    node_id TEXT NOT NULL,
+    -- When node_id took over the job
+    acquired_at TEXT NOT NULL,
+    -- When node_id last ran the job
+    last_run_at TEXT NOT NULL
+);
+
+-- Job runs: history of background job runs for the jobs admin API
+-- (see jobs::scheduler). Trimmed to the most recent runs per job.
+CREATE TABLE IF NOT EXISTS job_runs (
+    id TEXT PRIMARY KEY NOT NULL,
+    job TEXT NOT NULL,
+    node_id TEXT NOT NULL,
+    -- 'scheduled' or 'manual'
+    triggered_by TEXT NOT NULL,
+    -- 'succeeded' or 'failed'
+    status TEXT NOT NULL,
+    summary TEXT,
+    error TEXT,
+    started_at TEXT NOT NULL,
+    finished_at TEXT NOT NULL,
+    duration_ms INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_job_runs_job_started ON job_runs(job, started_at);
+
+-- Paused jobs: scheduled runs of these jobs are skipped on every node until
+-- they are resumed. Manual runs still go ahead.
+CREATE TABLE IF NOT EXISTS paused_jobs (
+    job TEXT PRIMARY KEY NOT NULL,
+    paused_at TEXT NOT NULL
+);
+
+-- Provider settings: runtime overrides for providers defined in the config
+-- file, set through the admin API. NULL columns keep the file's value.
+CREATE TABLE IF NOT EXISTS provider_settings (
+    id TEXT PRIMARY KEY NOT NULL,
+    provider_name TEXT NOT NULL UNIQUE,
+    enabled INTEGER,
+    -- JSON object of circuit breaker fields to override
+    circuit_breaker TEXT,
+    -- JSON object of retry fields to override
+    retry TEXT,
+    max_concurrent INTEGER,
+    updated_by TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+-- Idempotency keys: claims on client-supplied Idempotency-Key headers for
+-- completion endpoints, and the responses replayed to repeats of them.
+-- key_hash covers the caller, endpoint and key.
+CREATE TABLE IF NOT EXISTS idempotency_keys (
+    key_hash TEXT PRIMARY KEY NOT NULL,
+    request_hash TEXT NOT NULL,
+    -- 'in_progress' or 'completed'
+    status TEXT NOT NULL CHECK (status IN ('in_progress', 'completed')),
+    -- NULL while in progress, or when the response was too large to store
+    response_status INTEGER,
+    response_content_type TEXT,
+    response_body BLOB,
+    created_at TEXT NOT NULL,
+    expires_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_idempotency_keys_expires_at ON idempotency_keys(expires_at);
+
+-- Provider usage imports: usage and billing exports pulled from provider usage
+-- APIs or uploaded as CSV, for reconciling against usage_records. An import
+-- replaces all earlier imported rows for its provider and dates.
+CREATE TABLE IF NOT EXISTS provider_usage_imports (
+    id TEXT PRIMARY KEY NOT NULL,
+    provider TEXT NOT NULL,
+    -- 'openai', 'anthropic' or 'azure'
+    source TEXT NOT NULL CHECK (source IN ('openai', 'anthropic', 'azure')),
+    -- 'api' or 'csv'
+    method TEXT NOT NULL CHECK (method IN ('api', 'csv')),
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    row_count INTEGER NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_provider_usage_imports_provider
+    ON provider_usage_imports(provider, created_at DESC);
+
+-- Daily per-model usage as reported by the provider
+CREATE TABLE IF NOT EXISTS provider_usage (
+    provider TEXT NOT NULL,
+    date TEXT NOT NULL,
+    model TEXT NOT NULL,
+    import_id TEXT NOT NULL REFERENCES provider_usage_imports(id) ON DELETE CASCADE,
+    -- NULL when the source doesn't report it
+    request_count INTEGER,
+    input_tokens INTEGER NOT NULL DEFAULT 0,
+    output_tokens INTEGER NOT NULL DEFAULT 0,
+    cost_microcents INTEGER,
+    PRIMARY KEY (provider, date, model)
+);
+
+CREATE INDEX IF NOT EXISTS idx_provider_usage_date ON provider_usage(date);
+CREATE INDEX IF NOT EXISTS idx_provider_usage_import ON provider_usage(import_id);
+
+-- Model catalog entries added through the admin API, for models the
+-- models.dev catalog doesn't know (self-hosted, fine-tuned) or describes
+-- wrongly. They take precedence over models.dev data.
+CREATE TABLE IF NOT EXISTS model_catalog_entries (
+    id TEXT PRIMARY KEY NOT NULL,
+    -- Catalog provider ID
+    provider TEXT NOT NULL,
+    model TEXT NOT NULL,
+    -- JSON object of capabilities, limits, modalities, pricing and dates
+    metadata TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE (provider, model)
+);
+
+-- Fine-tuning jobs started through /v1/fine_tuning/jobs, recording who owns
+-- each provider job so it can be listed per org/project and billed once.
+CREATE TABLE IF NOT EXISTS fine_tuning_jobs (
+    id TEXT PRIMARY KEY NOT NULL,
+    provider TEXT NOT NULL,
+    provider_job_id TEXT NOT NULL,
+    org_id TEXT NOT NULL,
+    project_id TEXT,
+    team_id TEXT,
+    user_id TEXT,
+    api_key_id TEXT,
+    service_account_id TEXT,
+    -- Base model as sent to the provider
+    model TEXT NOT NULL,
+    -- Training and validation files as the client gave them
+    training_file TEXT NOT NULL,
+    validation_file TEXT,
+    -- Last status reported by the provider
+    status TEXT NOT NULL,
+    fine_tuned_model TEXT,
+    trained_tokens INTEGER,
+    -- Set once the finished job's usage is recorded and its model registered
+    completed_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_fine_tuning_jobs_org_created
+    ON fine_tuning_jobs(org_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_fine_tuning_jobs_incomplete
+    ON fine_tuning_jobs(updated_at) WHERE completed_at IS NULL;
+
+-- Copies of gateway files uploaded to providers, so a file is uploaded to each
+-- provider once and the copies are deleted along with it.
+CREATE TABLE IF NOT EXISTS provider_files (
+    id TEXT PRIMARY KEY NOT NULL,
+    file_id TEXT NOT NULL,
+    provider TEXT NOT NULL,
+    provider_file_id TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    UNIQUE (file_id, provider)
+);
+
+-- Agents: a model with a system prompt and server-executed tools, defined by
+-- admins and run through /v1/agents/{agent_id}/runs
+CREATE TABLE IF NOT EXISTS agents (
+    id TEXT PRIMARY KEY NOT NULL,
+    org_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT,
+    model TEXT NOT NULL,
+    instructions TEXT,
+    -- JSON array of Responses API tool definitions
+    tools TEXT NOT NULL DEFAULT '[]',
+    -- JSON array of vector store IDs searched with file_search
+    vector_store_ids TEXT NOT NULL DEFAULT '[]',
+    max_iterations INTEGER,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE (org_id, name)
+);
+
+-- Runs of an agent, with the caller and usage they are attributed to
+CREATE TABLE IF NOT EXISTS agent_runs (
+    id TEXT PRIMARY KEY NOT NULL,
+    agent_id TEXT NOT NULL REFERENCES agents(id) ON DELETE CASCADE,
+    org_id TEXT NOT NULL,
+    project_id TEXT,
+    user_id TEXT,
+    api_key_id TEXT,
+    service_account_id TEXT,
+    provider TEXT NOT NULL,
+    model TEXT NOT NULL,
+    status TEXT NOT NULL,
+    -- JSON input the run was started with
+    input TEXT NOT NULL,
+    output_text TEXT,
+    error TEXT,
+    input_tokens INTEGER NOT NULL DEFAULT 0,
+    output_tokens INTEGER NOT NULL DEFAULT 0,
+    cost_microcents INTEGER,
+    created_at TEXT NOT NULL,
+    completed_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_agent_runs_agent_created
+    ON agent_runs(agent_id, created_at DESC);
+
+-- Output items of a run (messages, tool calls and their outputs), in order
+CREATE TABLE IF NOT EXISTS agent_run_steps (
+    id TEXT PRIMARY KEY NOT NULL,
+    run_id TEXT NOT NULL REFERENCES agent_runs(id) ON DELETE CASCADE,
+    sequence INTEGER NOT NULL,
+    step_type TEXT NOT NULL,
+    -- JSON Responses API output item
+    content TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    UNIQUE (run_id, sequence)
+);
+
+-- Scheduled tasks: a prompt run on a cron schedule, with the output appended
+-- to a conversation or sent to a webhook
+CREATE TABLE IF NOT EXISTS scheduled_tasks (
+    id TEXT PRIMARY KEY NOT NULL,
+    org_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT,
+    -- Five-field cron expression, evaluated in UTC
+    schedule TEXT NOT NULL,
+    model TEXT NOT NULL,
+    instructions TEXT,
+    prompt TEXT NOT NULL,
+    -- 'conversation' or 'webhook'
+    delivery_type TEXT NOT NULL,
+    conversation_id TEXT,
+    webhook_url TEXT,
+    webhook_secret TEXT,
+    enabled INTEGER NOT NULL DEFAULT 1,
+    next_run_at TEXT,
+    last_run_at TEXT,
+    consecutive_failures INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE (org_id, name)
+);
+
+CREATE INDEX IF NOT EXISTS idx_scheduled_tasks_due
+    ON scheduled_tasks(next_run_at) WHERE enabled = 1;
+
+-- Run history of scheduled tasks, pruned to the newest runs per task
+CREATE TABLE IF NOT EXISTS scheduled_task_runs (
+    id TEXT PRIMARY KEY NOT NULL,
+    task_id TEXT NOT NULL REFERENCES scheduled_tasks(id) ON DELETE CASCADE,
+    status TEXT NOT NULL,
+    output_text TEXT,
+    error TEXT,
+    input_tokens INTEGER NOT NULL DEFAULT 0,
+    output_tokens INTEGER NOT NULL DEFAULT 0,
+    cost_microcents INTEGER,
+    started_at TEXT NOT NULL,
+    finished_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_scheduled_task_runs_task_started
+    ON scheduled_task_runs(task_id, started_at DESC);
+
+-- Invitations: an email address invited to join an organization (and
+-- optionally one of its teams). Only a SHA-256 hash of the invite token is
+-- stored. Expiry is derived from `expires_at`; `status` only records whether
+-- the invite was accepted or revoked.
+CREATE TABLE IF NOT EXISTS invitations (
+    id TEXT PRIMARY KEY NOT NULL,
+    org_id TEXT NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    team_id TEXT REFERENCES teams(id) ON DELETE CASCADE,
+    -- Lowercased
+    email TEXT NOT NULL,
+    role TEXT NOT NULL,
+    token_hash TEXT NOT NULL UNIQUE,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'accepted', 'revoked')),
+    invited_by TEXT,
+    expires_at TEXT NOT NULL,
+    send_count INTEGER NOT NULL DEFAULT 0,
+    last_sent_at TEXT,
+    accepted_at TEXT,
+    accepted_by TEXT,
+    revoked_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_invitations_org_created
+    ON invitations(org_id, created_at DESC);
+
+CREATE INDEX IF NOT EXISTS idx_invitations_pending_email
+    ON invitations(email) WHERE status = 'pending';
+
+-- Notification email send log, for troubleshooting delivery. Bodies aren't
+-- stored. Pruned by the retention worker.
+CREATE TABLE IF NOT EXISTS email_send_log (
+    id TEXT PRIMARY KEY NOT NULL,
+    org_id TEXT REFERENCES organizations(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL,
+    from_address TEXT NOT NULL,
+    -- JSON array of addresses
+    recipients TEXT NOT NULL,
+    subject TEXT NOT NULL,
+    status TEXT NOT NULL CHECK (status IN ('sent', 'failed', 'rate_limited')),
+    error TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_email_send_log_created
+    ON email_send_log(created_at DESC);
+
+CREATE INDEX IF NOT EXISTS idx_email_send_log_org_created
+    ON email_send_log(org_id, created_at DESC);
+
+-- Role elevations: time-boxed break-glass changes to a member's organization
+-- role. `previous_role` is captured on approval and restored when the
+-- elevation is revoked or expires. A member has at most one open request.
+CREATE TABLE IF NOT EXISTS role_elevations (
+    id TEXT PRIMARY KEY NOT NULL,
+    org_id TEXT NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    role TEXT NOT NULL,
+    previous_role TEXT,
+    justification TEXT NOT NULL,
+    duration_hours INTEGER NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'active', 'denied', 'revoked', 'expired')),
+    requested_by TEXT,
+    decided_by TEXT,
+    decision_note TEXT,
+    decided_at TEXT,
+    expires_at TEXT,
+    ended_at TEXT,
+    ended_by TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_role_elevations_org_created
+    ON role_elevations(org_id, created_at DESC);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_role_elevations_open
+    ON role_elevations(org_id, user_id) WHERE status IN ('pending', 'active');
+
+CREATE INDEX IF NOT EXISTS idx_role_elevations_active_expires
+    ON role_elevations(expires_at) WHERE status = 'active';
+
+-- Custom HTTP tools registered per organization through the admin API.
+-- Requests opt in by declaring a function tool with the same name.
+CREATE TABLE IF NOT EXISTS http_tools (
+    id TEXT PRIMARY KEY NOT NULL,
+    org_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT,
+    url TEXT NOT NULL,
+    -- JSON object of request headers; values are encrypted when field
+    -- encryption is configured
+    headers TEXT NOT NULL DEFAULT '{}',
+    -- JSON Schema for the call arguments
+    parameters TEXT,
+    timeout_secs INTEGER NOT NULL,
+    max_response_bytes INTEGER NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE (org_id, name)
+);
@@ -0,0 +1,151 @@
+//! OpenAI-compatible fine-tuning API types.
+//!
+//! Types for the fine-tuning job endpoints:
+//! - POST /v1/fine_tuning/jobs - Create a fine-tuning job
+//! - GET /v1/fine_tuning/jobs - List fine-tuning jobs
+//! - GET /v1/fine_tuning/jobs/{id} - Retrieve a fine-tuning job
+//! - POST /v1/fine_tuning/jobs/{id}/cancel - Cancel a fine-tuning job
+
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// Request body for `POST /v1/fine_tuning/jobs`.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CreateFineTuningJobRequest {
+    /// Base model to fine-tune. Routed like any other model name, so
+    /// `provider/model` selects the provider.
+    #[validate(length(min = 1, max = 255))]
+    pub model: String,
+
+    /// Training data in JSONL. Either a file uploaded to the gateway with
+    /// `purpose = "fine-tune"` (`file-<uuid>`), which is copied to the
+    /// provider, or a file ID of the provider's own.
+    #[validate(length(min = 1))]
+    pub training_file: String,
+
+    /// Validation data, given the same way as `training_file`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validation_file: Option<String>,
+
+    /// Training hyperparameters (deprecated by OpenAI in favor of `method`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "utoipa", schema(value_type = Option<Object>))]
+    pub hyperparameters: Option<serde_json::Value>,
+
+    /// Fine-tuning method (`supervised`, `dpo` or `reinforcement`) and its
+    /// hyperparameters.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "utoipa", schema(value_type = Option<Object>))]
+    pub method: Option<serde_json::Value>,
+
+    /// Up to 64 characters added to the fine-tuned model name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(length(max = 64))]
+    pub suffix: Option<String>,
+
+    /// Seed for reproducible training.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<i64>,
+
+    /// Key-value metadata attached to the job.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<std::collections::HashMap<String, String>>,
+}
+
+/// A fine-tuning job as the provider reports it.
+///
+/// Fields the gateway doesn't interpret are kept in `extra` and passed
+/// through to the client.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct FineTuningJob {
+    pub id: String,
+    #[serde(default = "default_object")]
+    pub object: String,
+    /// Base model being fine-tuned
+    pub model: String,
+    /// `validating_files`, `queued`, `running`, `succeeded`, `failed` or
+    /// `cancelled`
+    pub status: String,
+    /// Name of the resulting model, once the job succeeds
+    #[serde(default)]
+    pub fine_tuned_model: Option<String>,
+    /// Billable tokens processed, once the job finishes
+    #[serde(default)]
+    pub trained_tokens: Option<i64>,
+    pub training_file: String,
+    #[serde(default)]
+    pub validation_file: Option<String>,
+    /// Unix timestamp (seconds)
+    pub created_at: i64,
+    /// Unix timestamp (seconds) the job finished
+    #[serde(default)]
+    pub finished_at: Option<i64>,
+    /// Why the job failed
+    #[serde(default)]
+    #[cfg_attr(feature = "utoipa", schema(value_type = Option<Object>))]
+    pub error: Option<serde_json::Value>,
+    #[serde(flatten)]
+    #[cfg_attr(feature = "utoipa", schema(value_type = Object))]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl FineTuningJob {
+    /// Whether the job has stopped and its status won't change again.
+    pub fn is_finished(&self) -> bool {
+        is_terminal_status(&self.status)
+    }
+}
+
+/// Whether a fine-tuning job status is final.
+pub fn is_terminal_status(status: &str) -> bool {
+    matches!(status, "succeeded" | "failed" | "cancelled")
+}
+
+fn default_object() -> String {
+    "fine_tuning.job".to_string()
+}
+
+/// Paginated list of fine-tuning jobs (OpenAI-compatible).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct FineTuningJobList {
+    /// Always "list"
+    pub object: String,
+    pub data: Vec<FineTuningJob>,
+    pub has_more: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_job_keeps_unknown_fields() {
+        let job: FineTuningJob = serde_json::from_value(json!({
+            "id": "ftjob-abc",
+            "object": "fine_tuning.job",
+            "model": "gpt-4o-mini-2024-07-18",
+            "status": "succeeded",
+            "fine_tuned_model": "ft:gpt-4o-mini-2024-07-18:acme::9xyz",
+            "trained_tokens": 12345,
+            "training_file": "file-abc",
+            "created_at": 1721764800,
+            "finished_at": 1721768400,
+            "organization_id": "org-123",
+            "result_files": ["file-res"]
+        }))
+        .unwrap();
+
+        assert!(job.is_finished());
+        assert_eq!(job.trained_tokens, Some(12345));
+        assert_eq!(job.extra["organization_id"], "org-123");
+
+        let value = serde_json::to_value(&job).unwrap();
+        assert_eq!(value["result_files"], json!(["file-res"]));
+        assert!(value["error"].is_null());
+    }
+}
@@ -2,6 +2,7 @@ pub mod audio;
 pub mod chat_completion;
 pub mod completions;
 pub mod embeddings;
+pub mod fine_tuning;
 pub mod images;
 pub mod responses;
 
@@ -9,6 +10,7 @@ pub use audio::{CreateSpeechRequest, CreateTranscriptionRequest, CreateTranslati
 pub use chat_completion::{CreateChatCompletionPayload, Message, MessageContent, ReasoningEffort};
 pub use completions::CreateCompletionPayload;
 pub use embeddings::CreateEmbeddingPayload;
+pub use fine_tuning::{CreateFineTuningJobRequest, FineTuningJob, FineTuningJobList};
 #[cfg(feature = "utoipa")]
 pub use images::ImagesResponse;
 pub use images::{
@@ -408,7 +408,9 @@ pub struct ResponsesReasoning {
 // 1. File search returns results numbered as Source 1, Source 2, etc.
 // 2. Model generates response text with citation markers: "According to [Source 1]..."
 // 3. CitationTracker parses markers and creates FileCitation annotations
-// 4. Annotations are injected into `response.content_part.done` SSE events
+// 4. Annotations are injected into the `output_text` parts of
+//    `response.content_part.done`, `response.output_item.done` and the terminal
+//    `response.completed` events, so they are also stored with the response
 //
 // ## Frontend Rendering
 //
@@ -417,6 +419,8 @@ pub struct ResponsesReasoning {
 // 2. Use `index` to locate citation markers in the text
 // 3. Optionally replace markers with interactive citation UI elements
 // 4. Link citations to files using `file_id` for navigation/preview
+// 5. Use `chunk_start`/`chunk_end` (character offsets in the source file) to
+//    jump to the cited passage
 //
 // ## Example Response
 //
@@ -429,7 +433,12 @@ pub struct ResponsesReasoning {
 //       "type": "file_citation",
 //       "file_id": "file-abc123",
 //       "filename": "q3_report.pdf",
-//       "index": 13
+//       "index": 13,
+//       "vector_store_id": "9b2c0c8e-...",
+//       "chunk_id": "4f1a7d2e-...",
+//       "chunk_start": 1840,
+//       "chunk_end": 2912,
+//       "score": 0.87
 //     }
 //   ]
 // }
@@ -550,6 +559,23 @@ pub enum ResponsesAnnotation {
         filename: String,
         /// Byte offset where the citation marker (e.g., `[Source 1]`) starts.
         index: u64,
+        /// Hadrian extension: vector store the cited chunk was retrieved from.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        vector_store_id: Option<String>,
+        /// Hadrian extension: ID of the cited chunk.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        chunk_id: Option<String>,
+        /// Hadrian extension: character offset of the cited chunk in the
+        /// source file.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        chunk_start: Option<i32>,
+        /// Hadrian extension: character end offset of the cited chunk in the
+        /// source file.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        chunk_end: Option<i32>,
+        /// Hadrian extension: relevance score of the cited chunk (0.0 to 1.0).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        score: Option<f64>,
     },
     /// Citation to a URL from web search.
     UrlCitation {
@@ -2919,6 +2945,14 @@ pub struct ResponsesIncompleteDetails {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResponsesUsageInputTokensDetails {
     pub cached_tokens: i64,
+    /// **Hadrian Extension:** Tokens written to the prompt cache
+    /// (Anthropic/Bedrock), billed at the cache write rate.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub cache_creation_input_tokens: i64,
+}
+
+fn is_zero(v: &i64) -> bool {
+    *v == 0
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -3553,7 +3587,10 @@ mod usage_tests {
     fn usage(input: i64, output: i64, cost: Option<f64>) -> ResponsesUsage {
         ResponsesUsage {
             input_tokens: input,
-            input_tokens_details: ResponsesUsageInputTokensDetails { cached_tokens: 1 },
+            input_tokens_details: ResponsesUsageInputTokensDetails {
+                cached_tokens: 1,
+                cache_creation_input_tokens: 0,
+            },
             output_tokens: output,
             output_tokens_details: ResponsesUsageOutputTokensDetails {
                 reasoning_tokens: 2,
@@ -42,7 +42,7 @@ use crate::{
     usage_buffer,
 };
 #[cfg(feature = "server")]
-use crate::{middleware, routes};
+use crate::{middleware, routes, transforms};
 
 /// Embedded UI assets from ui/dist directory.
 /// These are compiled into the binary at build time.
@@ -317,13 +317,33 @@ pub struct AppState {
     /// Registry of circuit breakers for providers.
     /// Shared across requests to persist failure tracking.
     pub circuit_breakers: providers::CircuitBreakerRegistry,
+    /// Per-provider fair queues that share capacity between tenants.
+    pub fair_queues: providers::FairQueueRegistry,
+    /// Per-provider adaptive concurrency limits.
+    pub adaptive_limiters: providers::AdaptiveConcurrencyRegistry,
+    /// Runtime settings for static providers, set through the admin API and
+    /// applied on top of the config file.
+    pub provider_settings: providers::ProviderSettingsRegistry,
+    /// Per-API-key burst smoothing, if `limits.rate_limits.smoothing` is enabled.
+    #[cfg(feature = "server")]
+    pub request_smoother: Option<Arc<crate::middleware::util::smoothing::RequestSmoother>>,
+    /// Request-per-minute token buckets, if `limits.rate_limits.window_type`
+    /// is `token_bucket`.
+    pub token_buckets: Option<Arc<crate::cache::TokenBucketLimiter>>,
     /// Registry of provider health check states.
     /// Updated by background health checker, queried by admin API.
     pub provider_health: jobs::ProviderHealthStateRegistry,
+    /// Background jobs registered on this node, for the jobs admin API.
+    pub job_scheduler: Arc<jobs::JobScheduler>,
     /// Task tracker for background tasks (usage logging, etc.)
     /// Ensures all spawned tasks complete during graceful shutdown.
     #[cfg(feature = "server")]
     pub task_tracker: TaskTracker,
+    /// Draining state, started by `POST /admin/v1/system/drain` or a
+    /// shutdown signal. Fails readiness and turns away new data-plane
+    /// requests while counting those still in flight.
+    #[cfg(feature = "server")]
+    pub drain: Arc<middleware::DrainState>,
     /// Bounded channel + drainer for partial-usage logging from
     /// `UsageTrackingStream::Drop`, which can fire outside a runtime context
     /// (so it cannot safely spawn tasks of its own).
@@ -341,9 +361,16 @@ pub struct AppState {
     /// Routes incoming JWTs to the correct org-scoped validator by issuer.
     #[cfg(feature = "jwt")]
     pub gateway_jwt_registry: Option<Arc<auth::GatewayJwtRegistry>>,
+    /// Validators for externally-issued data-plane JWTs (`[[auth.jwt]]`).
+    #[cfg(feature = "jwt")]
+    pub bearer_jwt: Option<Arc<auth::BearerJwtAuthenticator>>,
     /// Registry of per-organization RBAC policies.
     /// Loaded from org_rbac_policies table at startup for per-org authorization.
     pub policy_registry: Option<Arc<authz::PolicyRegistry>>,
+    /// Compiled per-organization request policies, loaded per org on first
+    /// use. `None` without a database or with `[features.request_policies]`
+    /// disabled.
+    pub request_policies: Option<Arc<authz::RequestPolicyRegistry>>,
     /// Async buffer for usage log entries.
     /// Batches writes to reduce database pressure.
     #[cfg(feature = "concurrency")]
@@ -351,6 +378,8 @@ pub struct AppState {
     /// Response cache for chat completions.
     /// Caches deterministic responses to reduce latency and costs.
     pub response_cache: Option<Arc<cache::ResponseCache>>,
+    /// Cache for `/v1/embeddings` responses, with its own TTL and size limit.
+    pub embeddings_cache: Option<Arc<cache::EmbeddingsCache>>,
     /// Semantic cache for chat completions.
     /// Uses vector similarity to find cached responses for semantically similar requests.
     pub semantic_cache: Option<Arc<cache::SemanticCache>>,
@@ -360,6 +389,28 @@ pub struct AppState {
     /// Output guardrails evaluator for post-response content filtering.
     /// Evaluates LLM output against guardrails policies before returning to the user.
     pub output_guardrails: Option<Arc<guardrails::OutputGuardrails>>,
+    /// Request/response transformation pipeline from `[features.transforms]`.
+    /// Applied by the transforms middleware on the data-plane endpoints.
+    #[cfg(feature = "server")]
+    pub transforms: Option<Arc<transforms::TransformPipeline>>,
+    /// Mirroring of chat completions per `[features.shadow_traffic]`.
+    /// `None` without a database or when no rule is active.
+    #[cfg(feature = "server")]
+    pub shadow_traffic: Option<Arc<services::shadow_traffic::ShadowTraffic>>,
+    /// Claims and stored responses for `Idempotency-Key`s per
+    /// `[features.idempotency]`. `None` when disabled.
+    #[cfg(feature = "server")]
+    pub idempotency: Option<Arc<services::idempotency::IdempotencyStore>>,
+    /// Per-model tokenizers from `[features.token_counting]`, used by
+    /// `/api/v1/tokenize` and to size limit reservations. `None` when disabled.
+    pub token_counter: Option<Arc<services::token_counter::TokenCounter>>,
+    /// Splitting and coalescing of `/v1/embeddings` requests per
+    /// `[features.embeddings]`. `None` when disabled.
+    pub embedding_batcher: Option<Arc<services::embedding_batcher::EmbeddingBatcher>>,
+    /// Notification emails per `[notifications.email]`: invitations, spend
+    /// alerts, access review reminders and DLQ failures. `None` when email
+    /// isn't configured.
+    pub email_notifier: Option<Arc<services::EmailNotifier>>,
     /// Event bus for broadcasting server events to WebSocket subscribers.
     /// Used for real-time monitoring dashboards and push notifications.
     pub event_bus: Arc<events::EventBus>,
@@ -497,7 +548,12 @@ impl AppState {
             config::CacheConfig::Redis(cfg) => {
                 #[cfg(feature = "redis")]
                 {
-                    Some(Arc::new(cache::RedisCache::from_config(cfg).await?))
+                    let redis: Arc<dyn cache::Cache> =
+                        Arc::new(cache::RedisCache::from_config(cfg).await?);
+                    match &cfg.l1 {
+                        Some(l1) => Some(Arc::new(cache::TieredCache::new(redis, l1))),
+                        None => Some(redis),
+                    }
                 }
                 #[cfg(not(feature = "redis"))]
                 {
@@ -722,6 +778,49 @@ impl AppState {
             }
         };
 
+        // Field-level encryption needs the secrets manager to load its KEKs,
+        // so it is wired into services here rather than alongside the cache.
+        if let Some(fe_config) = &config.features.field_encryption
+            && let Some(services) = services.as_mut()
+        {
+            let encryptor = Arc::new(
+                services::FieldEncryptor::from_config(fe_config, secrets.as_ref())
+                    .await
+                    .map_err(|e| format!("Failed to initialize field encryption: {e}"))?,
+            );
+            tracing::info!(
+                active_key = %fe_config.active_key,
+                key_count = fe_config.keys.len(),
+                enabled = fe_config.enabled,
+                "Field-level encryption initialized"
+            );
+            let db = db
+                .clone()
+                .expect("services exist only when db is configured");
+            services.conversations = std::mem::replace(
+                &mut services.conversations,
+                services::ConversationService::new(db.clone()),
+            )
+            .with_encryption(Some(encryptor.clone()));
+            services.templates = std::mem::replace(
+                &mut services.templates,
+                services::TemplateService::new(db.clone()),
+            )
+            .with_encryption(Some(encryptor.clone()));
+            services.http_tools = std::mem::replace(
+                &mut services.http_tools,
+                services::HttpToolService::new(db.clone()),
+            )
+            .with_encryption(Some(encryptor.clone()));
+            services.step_up = std::mem::replace(
+                &mut services.step_up,
+                services::StepUpService::new(db.clone()),
+            )
+            .with_encryption(Some(encryptor.clone()));
+            services.users = std::mem::replace(&mut services.users, services::UserService::new(db))
+                .with_encryption(Some(encryptor));
+        }
+
         // Initialize model catalog registry from embedded data (if available)
         let model_catalog = catalog::ModelCatalogRegistry::new();
         match catalog::embedded_catalog() {
@@ -744,6 +843,20 @@ impl AppState {
             }
         }
 
+        if let Some(db) = &db {
+            match model_catalog.load_custom_entries(db).await {
+                Ok(()) => {
+                    tracing::debug!(
+                        entry_count = model_catalog.custom_entry_count(),
+                        "Loaded custom model catalog entries"
+                    );
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, "Failed to load custom model catalog entries");
+                }
+            }
+        }
+
         // Initialize pricing from defaults + config + provider configs + catalog
         let pricing = Arc::new(pricing::PricingConfig::from_config_with_catalog(
             &config.pricing,
@@ -765,6 +878,23 @@ impl AppState {
             &config.providers,
             event_bus.clone(),
         );
+        let fair_queues = providers::FairQueueRegistry::from_config(&config.providers);
+        let adaptive_limiters =
+            providers::AdaptiveConcurrencyRegistry::from_config(&config.providers);
+        let provider_settings =
+            providers::ProviderSettingsRegistry::new(circuit_breakers.clone(), fair_queues.clone());
+        if let Some(db) = &db
+            && let Err(e) = provider_settings.load(db).await
+        {
+            tracing::warn!(error = %e, "Failed to load provider settings; using the config file");
+        }
+        #[cfg(feature = "server")]
+        let request_smoother = crate::middleware::util::smoothing::RequestSmoother::from_config(
+            &config.limits.rate_limits.smoothing,
+        )
+        .map(Arc::new);
+        let token_buckets =
+            crate::cache::TokenBucketLimiter::from_config(&config.limits.rate_limits).map(Arc::new);
 
         // Get session config from UI auth config
         // Note: Global OIDC config has been removed. Session config is used for per-org SSO.
@@ -892,6 +1022,22 @@ impl AppState {
             None
         };
 
+        // External issuers trusted for data-plane bearer JWTs. JWKS are fetched
+        // lazily on the first token from each issuer.
+        #[cfg(feature = "jwt")]
+        let bearer_jwt = if config.auth.jwt.is_empty() {
+            None
+        } else {
+            let url_validation_opts = crate::validation::UrlValidationOptions {
+                allow_loopback: config.server.allow_loopback_urls,
+                allow_private: config.server.allow_private_urls,
+            };
+            Some(Arc::new(
+                auth::BearerJwtAuthenticator::new(&config.auth.jwt, url_validation_opts)
+                    .expect("Failed to initialize auth.jwt validators"),
+            ))
+        };
+
         // Initialize per-org RBAC policy registry from database
         let policy_registry = if let (Some(svc), Some(db_pool)) = (&services, &db)
             && config.auth.rbac.enabled
@@ -963,16 +1109,34 @@ impl AppState {
             None
         };
 
+        // Per-org request policies are loaded lazily, one org at a time
+        let request_policies = match &db {
+            Some(db_pool) if config.features.request_policies.enabled => {
+                Some(Arc::new(authz::RequestPolicyRegistry::new(
+                    db_pool.org_request_policies(),
+                    cache.clone(),
+                    &config.features.request_policies,
+                    config.auth.rbac.max_expression_length,
+                )))
+            }
+            _ => None,
+        };
+
         // Initialize usage log buffer with configured buffer settings and EventBus
         #[cfg(feature = "concurrency")]
         let usage_buffer = {
             let buffer_config =
                 usage_buffer::UsageBufferConfig::from(&config.observability.usage.buffer);
-            let buffer = Arc::new(usage_buffer::UsageLogBuffer::with_event_bus(
-                buffer_config,
-                event_bus.clone(),
-            ));
-            Some(buffer)
+            let mut buffer =
+                usage_buffer::UsageLogBuffer::with_event_bus(buffer_config, event_bus.clone());
+            if let Some(wal_config) = &config.observability.usage.buffer.wal {
+                let wal = crate::usage_wal::create_usage_wal(wal_config)
+                    .await
+                    .map_err(|e| format!("Failed to initialize usage WAL: {}", e))?;
+                tracing::info!(wal = wal.name(), "Usage write-ahead log initialized");
+                buffer = buffer.with_wal(wal);
+            }
+            Some(Arc::new(buffer))
         };
 
         // Initialize response cache if configured and cache is available
@@ -999,6 +1163,23 @@ impl AppState {
             _ => None,
         };
 
+        let embeddings_cache = match (&config.features.response_caching, &cache) {
+            (Some(caching_config), Some(cache_instance))
+                if caching_config.enabled && caching_config.embeddings.enabled =>
+            {
+                tracing::info!(
+                    ttl_secs = caching_config.embeddings.ttl_secs,
+                    max_size_bytes = caching_config.embeddings.max_size_bytes,
+                    "Embeddings caching enabled"
+                );
+                Some(Arc::new(cache::EmbeddingsCache::new(
+                    cache_instance.clone(),
+                    caching_config.embeddings.clone(),
+                )))
+            }
+            _ => None,
+        };
+
         // Create the task tracker for background tasks
         #[cfg(feature = "server")]
         let task_tracker = TaskTracker::new();
@@ -1070,6 +1251,96 @@ impl AppState {
             None => None,
         };
 
+        // Initialize request/response transforms. A rule that can't be
+        // compiled (e.g. a missing WASM plugin) fails startup rather than
+        // silently dropping the transform.
+        #[cfg(feature = "server")]
+        let transforms = transforms::TransformPipeline::from_config(&config.features.transforms)
+            .map_err(|e| format!("Failed to initialize transforms: {}", e))?
+            .map(|pipeline| {
+                tracing::info!(rules = pipeline.len(), "Request transforms enabled");
+                Arc::new(pipeline)
+            });
+
+        // Shadow results are stored in the database, so mirroring needs one
+        #[cfg(feature = "server")]
+        let shadow_traffic =
+            match services::shadow_traffic::ShadowTraffic::new(&config.features.shadow_traffic) {
+                Some(_) if db.is_none() => {
+                    tracing::warn!("Shadow traffic rules are configured but require a database");
+                    None
+                }
+                Some(shadow) => {
+                    tracing::info!(
+                        rules = config.features.shadow_traffic.rules.len(),
+                        "Shadow traffic enabled"
+                    );
+                    Some(Arc::new(shadow))
+                }
+                None => None,
+            };
+
+        // Idempotency claims live in the database, or in the cache without one
+        #[cfg(feature = "server")]
+        let idempotency = if config.features.idempotency.enabled {
+            match services::idempotency::IdempotencyStore::new(
+                &config.features.idempotency,
+                db.clone(),
+                cache.clone(),
+            ) {
+                Some(store) => {
+                    tracing::info!(
+                        ttl_secs = config.features.idempotency.ttl_secs,
+                        "Idempotency keys enabled"
+                    );
+                    Some(Arc::new(store))
+                }
+                None => {
+                    tracing::warn!("Idempotency keys are enabled but require a database or cache");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let email_notifier =
+            config.notifications.email.as_ref().and_then(
+                |email| match services::EmailNotifier::new(email, db.clone()) {
+                    Ok(notifier) => Some(Arc::new(notifier)),
+                    Err(e) => {
+                        tracing::error!(
+                            error = %e,
+                            "Failed to configure SMTP; notification emails are disabled"
+                        );
+                        None
+                    }
+                },
+            );
+
+        // Tokenizer files are loaded up front so a bad path fails startup
+        let token_counter =
+            services::token_counter::TokenCounter::from_config(&config.features.token_counting)
+                .map_err(|e| format!("Failed to initialize token counting: {}", e))?
+                .map(|counter| {
+                    tracing::info!(
+                        tokenizers = config.features.token_counting.tokenizers.len(),
+                        "Token counting enabled"
+                    );
+                    Arc::new(counter)
+                });
+
+        let embedding_batcher = services::embedding_batcher::EmbeddingBatcher::new(
+            &config.features.embeddings,
+        )
+        .map(|batcher| {
+            tracing::info!(
+                coalesce = config.features.embeddings.coalesce.is_some(),
+                "Embeddings batching enabled"
+            );
+            Arc::new(batcher)
+        });
+
         // Initialize file search service if configured
         // This requires both semantic cache components (embedding service + vector store)
         // and file_search configuration
@@ -1318,6 +1589,8 @@ impl AppState {
             Arc::new(services::ProviderMetricsService::new())
         };
 
+        let job_scheduler = Arc::new(jobs::JobScheduler::new(db.clone()));
+
         let result = Ok(Self {
             http_client,
             config: Arc::new(config),
@@ -1328,10 +1601,19 @@ impl AppState {
             dlq,
             pricing,
             circuit_breakers,
+            fair_queues,
+            adaptive_limiters,
+            provider_settings,
+            #[cfg(feature = "server")]
+            request_smoother,
+            token_buckets,
             provider_health: jobs::ProviderHealthStateRegistry::new(),
+            job_scheduler,
             #[cfg(feature = "server")]
             task_tracker,
             #[cfg(feature = "server")]
+            drain: Arc::new(middleware::DrainState::new()),
+            #[cfg(feature = "server")]
             usage_drain,
             #[cfg(feature = "sso")]
             oidc_registry,
@@ -1339,13 +1621,26 @@ impl AppState {
             saml_registry,
             #[cfg(feature = "jwt")]
             gateway_jwt_registry,
+            #[cfg(feature = "jwt")]
+            bearer_jwt,
             policy_registry,
+            request_policies,
             #[cfg(feature = "concurrency")]
             usage_buffer,
             response_cache,
+            embeddings_cache,
             semantic_cache,
             input_guardrails,
             output_guardrails,
+            #[cfg(feature = "server")]
+            transforms,
+            #[cfg(feature = "server")]
+            shadow_traffic,
+            #[cfg(feature = "server")]
+            idempotency,
+            token_counter,
+            embedding_batcher,
+            email_notifier,
             event_bus,
             file_search_service,
             #[cfg(feature = "server")]
@@ -1489,7 +1784,7 @@ impl AppState {
         http_client: Client,
         task_tracker: &TaskTracker,
     ) -> Option<Arc<cache::SemanticCache>> {
-        #[cfg(not(feature = "database-postgres"))]
+        #[cfg(not(any(feature = "database-postgres", feature = "database-sqlite")))]
         let _ = &db;
         // Check if semantic caching is configured
         let semantic_config = match &config.features.response_caching {
@@ -1617,6 +1912,47 @@ impl AppState {
 
                 Arc::new(store)
             }
+            #[cfg(feature = "database-sqlite")]
+            config::SemanticVectorBackend::Sqlite {
+                table_name,
+                distance_metric,
+            } => {
+                let sqlite_pool = match db.and_then(|d| d.sqlite_pool()) {
+                    Some(pool) => pool.clone(),
+                    None => {
+                        tracing::warn!(
+                            "Semantic caching with the SQLite vector backend requires SQLite database. \
+                                 Configure [database] with type = \"sqlite\"."
+                        );
+                        return None;
+                    }
+                };
+
+                let store = cache::vector_store::SqliteVectorStore::new(
+                    sqlite_pool,
+                    table_name.clone(),
+                    semantic_config.embedding.dimensions,
+                    *distance_metric,
+                );
+
+                if let Err(e) = store.initialize().await {
+                    tracing::error!(
+                        error = %e,
+                        "Failed to initialize SQLite vector store for semantic caching"
+                    );
+                    return None;
+                }
+
+                Arc::new(store)
+            }
+            #[cfg(not(feature = "database-sqlite"))]
+            config::SemanticVectorBackend::Sqlite { .. } => {
+                tracing::warn!(
+                    "Semantic caching with the SQLite vector backend requires the 'database-sqlite' feature. \
+                         Rebuild with --features database-sqlite or use a different vector backend."
+                );
+                return None;
+            }
         };
 
         // Create the semantic cache with background worker
@@ -1814,7 +2150,7 @@ impl AppState {
         // Get vector backend configuration with priority:
         // 1. file_search.vector_backend (explicit RAG config - RECOMMENDED)
         // 2. response_caching.semantic.vector_backend (semantic cache config - for backward compat)
-        // 3. Default pgvector with "rag_chunks" table
+        // 3. Default "rag_chunks" table in the primary database (pgvector or SQLite)
         //
         // Using separate vector storage for RAG ensures:
         // - RAG chunks are stored in clearly named tables (rag_chunks vs semantic_cache_embeddings)
@@ -1908,6 +2244,54 @@ impl AppState {
 
                     Arc::new(store)
                 }
+                #[cfg(feature = "database-sqlite")]
+                config::RagVectorBackend::Sqlite {
+                    table_name,
+                    distance_metric,
+                } => {
+                    let sqlite_pool = match db.sqlite_pool() {
+                        Some(pool) => pool.clone(),
+                        None => {
+                            tracing::warn!(
+                                "File search with the SQLite vector backend requires SQLite database. \
+                                     Configure [database] with type = \"sqlite\"."
+                            );
+                            return None;
+                        }
+                    };
+
+                    // The store appends "_chunks", so strip it to make table_name
+                    // the chunks table itself
+                    let store = cache::vector_store::SqliteVectorStore::new(
+                        sqlite_pool,
+                        table_name.trim_end_matches("_chunks").to_string(),
+                        embedding_config.dimensions,
+                        *distance_metric,
+                    );
+
+                    if let Err(e) = store.initialize().await {
+                        tracing::error!(
+                            error = %e,
+                            "Failed to initialize SQLite vector store for file search"
+                        );
+                        return None;
+                    }
+
+                    tracing::info!(
+                        table_name = %table_name,
+                        "RAG using dedicated SQLite table"
+                    );
+
+                    Arc::new(store)
+                }
+                #[cfg(not(feature = "database-sqlite"))]
+                config::RagVectorBackend::Sqlite { .. } => {
+                    tracing::warn!(
+                        "File search with the SQLite vector backend requires the 'database-sqlite' feature. \
+                             Rebuild with --features database-sqlite or use a different vector backend."
+                    );
+                    return None;
+                }
             }
         } else if let Some(semantic_config) = config
             .features
@@ -1990,51 +2374,102 @@ impl AppState {
 
                     Arc::new(store)
                 }
+                #[cfg(feature = "database-sqlite")]
+                config::SemanticVectorBackend::Sqlite {
+                    table_name,
+                    distance_metric,
+                } => {
+                    let sqlite_pool = match db.sqlite_pool() {
+                        Some(pool) => pool.clone(),
+                        None => {
+                            tracing::warn!(
+                                "File search with the SQLite vector backend requires SQLite database. \
+                                     Configure [database] with type = \"sqlite\"."
+                            );
+                            return None;
+                        }
+                    };
+
+                    let store = cache::vector_store::SqliteVectorStore::new(
+                        sqlite_pool,
+                        table_name.clone(),
+                        embedding_config.dimensions,
+                        *distance_metric,
+                    );
+
+                    if let Err(e) = store.initialize().await {
+                        tracing::error!(
+                            error = %e,
+                            "Failed to initialize SQLite vector store for file search"
+                        );
+                        return None;
+                    }
+
+                    Arc::new(store)
+                }
+                #[cfg(not(feature = "database-sqlite"))]
+                config::SemanticVectorBackend::Sqlite { .. } => {
+                    tracing::warn!(
+                        "File search with the SQLite vector backend requires the 'database-sqlite' feature. \
+                             Rebuild with --features database-sqlite or use a different vector backend."
+                    );
+                    return None;
+                }
             }
         } else {
-            // Priority 3: Default pgvector with "rag_chunks" table
-            #[cfg(not(feature = "database-postgres"))]
-            {
-                tracing::warn!(
-                    "File search requires a vector store backend. Configure \
-                         [features.file_search.vector_backend] or rebuild with --features database-postgres."
-                );
-                return None;
-            }
+            // Priority 3: Default "rag_chunks" table in the primary database
+            match db.pool() {
+                #[cfg(feature = "database-postgres")]
+                crate::db::DbPoolRef::Postgres(pools) => {
+                    let store = cache::vector_store::PgvectorStore::new(
+                        pools.write.clone(),
+                        "rag".to_string(), // Creates "rag" for semantic + "rag_chunks" for RAG
+                        embedding_config.dimensions,
+                        config::PgvectorIndexType::IvfFlat,
+                        config::DistanceMetric::default(), // Cosine (default)
+                    );
 
-            #[cfg(feature = "database-postgres")]
-            {
-                let pg_pool = match db.pg_write_pool() {
-                    Some(pool) => pool.clone(),
-                    None => {
-                        tracing::warn!(
-                            "File search requires a vector store backend. Configure \
-                                 [features.file_search.vector_backend] or use PostgreSQL."
+                    if let Err(e) = store.initialize().await {
+                        tracing::error!(
+                            error = %e,
+                            "Failed to initialize pgvector store for file search"
                         );
                         return None;
                     }
-                };
 
-                // Use "rag_chunks" as the default table name (clear naming)
-                let store = cache::vector_store::PgvectorStore::new(
-                    pg_pool,
-                    "rag".to_string(), // Creates "rag" for semantic + "rag_chunks" for RAG
-                    embedding_config.dimensions,
-                    config::PgvectorIndexType::IvfFlat,
-                    config::DistanceMetric::default(), // Cosine (default)
-                );
+                    tracing::info!("RAG using default pgvector table 'rag_chunks'");
 
-                if let Err(e) = store.initialize().await {
-                    tracing::error!(
-                        error = %e,
-                        "Failed to initialize pgvector store for file search"
-                    );
-                    return None;
+                    Arc::new(store)
                 }
+                #[cfg(feature = "database-sqlite")]
+                crate::db::DbPoolRef::Sqlite(pool) => {
+                    let store = cache::vector_store::SqliteVectorStore::new(
+                        pool.clone(),
+                        "rag".to_string(),
+                        embedding_config.dimensions,
+                        config::DistanceMetric::default(),
+                    );
 
-                tracing::info!("RAG using default pgvector table 'rag_chunks'");
+                    if let Err(e) = store.initialize().await {
+                        tracing::error!(
+                            error = %e,
+                            "Failed to initialize SQLite vector store for file search"
+                        );
+                        return None;
+                    }
 
-                Arc::new(store)
+                    tracing::info!("RAG using default SQLite table 'rag_chunks'");
+
+                    Arc::new(store)
+                }
+                #[allow(unreachable_patterns)]
+                _ => {
+                    tracing::warn!(
+                        "File search requires a vector store backend. Configure \
+                             [features.file_search.vector_backend]."
+                    );
+                    return None;
+                }
             }
         };
 
@@ -2273,7 +2708,13 @@ pub fn build_app(config: &config::GatewayConfig, state: AppState) -> Router {
             // Apply middleware in order: admin_auth_middleware runs first,
             // then authz_middleware runs second (layers are applied in reverse order)
             // IP rate limiting runs before auth for defense in depth
-            let admin_routes = routes::admin::get_protected_admin_routes()
+            let admin_routes = routes::admin::get_protected_admin_routes();
+            #[cfg(feature = "graphql")]
+            let admin_routes = match &config.server.graphql {
+                Some(graphql) => admin_routes.merge(routes::admin::graphql::routes(graphql)),
+                None => admin_routes,
+            };
+            let admin_routes = admin_routes
                 .route_layer(axum::middleware::from_fn_with_state(
                     state.clone(),
                     middleware::authz_middleware,
@@ -2294,7 +2735,13 @@ pub fn build_app(config: &config::GatewayConfig, state: AppState) -> Router {
             // Apply permissive authz middleware so handlers can still require AuthzContext
             // (fail-closed pattern) but authorization checks will always pass
             // IP rate limiting still applied for DoS protection
-            let admin_routes = routes::admin::get_admin_routes()
+            let admin_routes = routes::admin::get_admin_routes();
+            #[cfg(feature = "graphql")]
+            let admin_routes = match &config.server.graphql {
+                Some(graphql) => admin_routes.merge(routes::admin::graphql::routes(graphql)),
+                None => admin_routes,
+            };
+            let admin_routes = admin_routes
                 .route_layer(axum::middleware::from_fn_with_state(
                     state.clone(),
                     middleware::permissive_authz_middleware,
@@ -2366,6 +2813,17 @@ pub fn build_app(config: &config::GatewayConfig, state: AppState) -> Router {
                 );
                 app = app.route("/auth/discover", discover_route);
             }
+
+            // Invite links land here before the user signs in
+            if !config.database.is_none() && config.features.invitations.enabled {
+                let accept_route = get(routes::auth_routes::accept_invitation).route_layer(
+                    axum::middleware::from_fn_with_state(
+                        state.clone(),
+                        middleware::rate_limit_middleware,
+                    ),
+                );
+                app = app.route("/auth/invitations/accept", accept_route);
+            }
         } else if !config.database.is_none() {
             // When SSO feature is enabled but auth is disabled and database is available,
             // add /auth/me with permissive middleware
@@ -2398,6 +2856,16 @@ pub fn build_app(config: &config::GatewayConfig, state: AppState) -> Router {
         );
     }
 
+    // Federation hub ingest. Satellites authenticate with per-satellite
+    // tokens from `[[federation.hub.satellites]]`, checked in the handler.
+    if !config.database.is_none() && config.federation.hub.is_some() {
+        let federation_route = post(routes::federation::ingest_report).route_layer(
+            axum::middleware::from_fn_with_state(state.clone(), middleware::rate_limit_middleware),
+        );
+        app = app.route("/federation/v1/reports", federation_route);
+        tracing::info!("Federation hub enabled at /federation/v1/reports");
+    }
+
     // Add SAML routes if database is configured (SAML uses per-org SSO configs from database)
     // These routes are separate from OIDC since they use HTTP-POST binding and different flows
     #[cfg(feature = "saml")]
@@ -2419,6 +2887,40 @@ pub fn build_app(config: &config::GatewayConfig, state: AppState) -> Router {
         tracing::debug!("SAML 2.0 authentication routes enabled at /auth/saml/");
     }
 
+    // Passkey registration and sign-in. The handlers resolve the session
+    // cookie themselves so `passkeys = "required"` can't lock users out.
+    #[cfg(feature = "webauthn")]
+    if !config.database.is_none()
+        && config.auth.admin.passkeys != crate::config::PasskeyPolicy::Disabled
+    {
+        let webauthn_routes = Router::new()
+            .route("/register/start", post(routes::webauthn::register_start))
+            .route("/register/finish", post(routes::webauthn::register_finish))
+            .route(
+                "/authenticate/start",
+                post(routes::webauthn::authenticate_start),
+            )
+            .route(
+                "/authenticate/finish",
+                post(routes::webauthn::authenticate_finish),
+            )
+            .route("/credentials", get(routes::webauthn::list_credentials))
+            .route(
+                "/credentials/{id}",
+                axum::routing::delete(routes::webauthn::delete_credential),
+            )
+            .route_layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                middleware::rate_limit_middleware,
+            ));
+
+        app = app.nest("/auth/webauthn", webauthn_routes);
+        tracing::info!(
+            policy = ?config.auth.admin.passkeys,
+            "Passkey authentication routes enabled at /auth/webauthn/"
+        );
+    }
+
     // Add SCIM routes for automated user provisioning from IdPs
     // SCIM requires database to be configured (for token storage and user/group mappings)
     #[cfg(feature = "sso")]
@@ -2447,7 +2949,10 @@ pub fn build_app(config: &config::GatewayConfig, state: AppState) -> Router {
     // Add request ID middleware first, then cookies layer for session management
     // Security headers are added to all responses
     app = app
-        .layer(axum::middleware::from_fn(middleware::request_id_middleware))
+        .layer(axum::middleware::from_fn_with_state(
+            config.server.error_format,
+            middleware::request_id_middleware,
+        ))
         .layer(tower_cookies::CookieManagerLayer::new())
         .layer(axum::middleware::from_fn_with_state(
             state.clone(),
@@ -0,0 +1,248 @@
+//! Externally-issued JWT bearer authentication for the data plane.
+//!
+//! Lets callers present a JWT minted by their own identity provider instead of
+//! a gateway API key. Each `[[auth.jwt]]` entry trusts one issuer; tokens are
+//! routed to it by their `iss` claim, verified against the issuer's JWKS, and
+//! mapped onto an organization, project, and user through configurable claim
+//! paths.
+
+#[cfg(feature = "jwt")]
+use std::collections::HashMap;
+
+#[cfg(feature = "jwt")]
+use serde_json::Value;
+use uuid::Uuid;
+
+#[cfg(feature = "jwt")]
+use super::{AuthError, jwt::JwtValidator};
+#[cfg(feature = "jwt")]
+use crate::{config::BearerJwtConfig, validation::UrlValidationOptions};
+
+/// Authentication details for a request carrying an externally-issued JWT.
+#[derive(Debug, Clone)]
+pub struct BearerJwtAuth {
+    /// Issuer (`iss` claim) the token was validated against
+    pub issuer: String,
+    /// Subject taken from the configured `subject_claim`; recorded in usage logs
+    pub subject: String,
+    /// Gateway user matched through `user_claim`
+    pub user_id: Option<Uuid>,
+    /// Organization resolved from `org_claim`
+    pub org_id: Option<Uuid>,
+    /// Project resolved from `project_claim`
+    pub project_id: Option<Uuid>,
+    /// Roles from `roles_claim`
+    pub roles: Vec<String>,
+    pub email: Option<String>,
+    pub name: Option<String>,
+}
+
+/// The `users.external_id` a token's `user_claim` value is matched against:
+/// `<issuer>|<value>`.
+#[cfg(feature = "jwt")]
+pub fn bearer_jwt_external_id(issuer: &str, user: &str) -> String {
+    format!("{issuer}|{user}")
+}
+
+/// Claims pulled out of a verified token, before database resolution.
+#[cfg(feature = "jwt")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BearerJwtClaims {
+    pub issuer: String,
+    pub subject: String,
+    /// Value of `user_claim`, matched against `users.external_id` as
+    /// `<issuer>|<value>` (see [`bearer_jwt_external_id`])
+    pub user: Option<String>,
+    /// Value of `org_claim` (slug or ID)
+    pub org: Option<String>,
+    /// Value of `project_claim` (slug or ID)
+    pub project: Option<String>,
+    pub roles: Vec<String>,
+    pub email: Option<String>,
+    pub name: Option<String>,
+    /// Whether the issuer requires a resolvable organization
+    pub require_org: bool,
+}
+
+/// Validators for the configured external issuers, keyed by issuer.
+///
+/// Each validator keeps its own JWKS cache for the lifetime of the process.
+#[cfg(feature = "jwt")]
+pub struct BearerJwtAuthenticator {
+    issuers: HashMap<String, (BearerJwtConfig, JwtValidator)>,
+}
+
+#[cfg(feature = "jwt")]
+impl BearerJwtAuthenticator {
+    pub fn new(
+        configs: &[BearerJwtConfig],
+        url_validation_opts: UrlValidationOptions,
+    ) -> Result<Self, AuthError> {
+        let mut issuers = HashMap::with_capacity(configs.len());
+        for config in configs {
+            let validator =
+                JwtValidator::with_options(config.validator_config(), url_validation_opts)?;
+            issuers.insert(config.issuer.clone(), (config.clone(), validator));
+        }
+        Ok(Self { issuers })
+    }
+
+    /// Whether tokens from `issuer` are handled here.
+    pub fn handles_issuer(&self, issuer: &str) -> bool {
+        self.issuers.contains_key(issuer)
+    }
+
+    /// Verify a token from a configured issuer and extract the mapped claims.
+    ///
+    /// Returns `Ok(None)` when `issuer` isn't configured, so the caller can
+    /// try other credential types.
+    pub async fn validate(
+        &self,
+        issuer: &str,
+        token: &str,
+    ) -> Result<Option<BearerJwtClaims>, AuthError> {
+        let Some((config, validator)) = self.issuers.get(issuer) else {
+            return Ok(None);
+        };
+        let claims = validator.validate(token).await?;
+        let claims = serde_json::to_value(&claims)
+            .map_err(|e| AuthError::Internal(format!("Failed to read JWT claims: {e}")))?;
+        map_claims(config, &claims).map(Some)
+    }
+}
+
+/// Map verified claims through the issuer's configured claim paths.
+#[cfg(feature = "jwt")]
+fn map_claims(config: &BearerJwtConfig, claims: &Value) -> Result<BearerJwtClaims, AuthError> {
+    let text = |path: &str| claim(claims, path).and_then(claim_string);
+
+    let subject = text(&config.subject_claim).ok_or_else(|| {
+        tracing::debug!(claim = %config.subject_claim, "JWT missing subject claim");
+        AuthError::InvalidToken
+    })?;
+
+    let roles = match config.roles_claim.as_deref().and_then(|p| claim(claims, p)) {
+        Some(Value::Array(values)) => values.iter().filter_map(claim_string).collect(),
+        Some(value) => claim_string(value).into_iter().collect(),
+        None => Vec::new(),
+    };
+
+    Ok(BearerJwtClaims {
+        issuer: config.issuer.clone(),
+        subject,
+        user: config.user_claim.as_deref().and_then(text),
+        org: config.org_claim.as_deref().and_then(text),
+        project: config.project_claim.as_deref().and_then(text),
+        roles,
+        email: text("email"),
+        name: text("name"),
+        require_org: config.require_org,
+    })
+}
+
+/// Look up a claim by path. An exact top-level match wins, so namespaced
+/// claims like `https://example.com/org` work; otherwise the path is split on
+/// `.` and walked through nested objects.
+#[cfg(feature = "jwt")]
+fn claim<'a>(claims: &'a Value, path: &str) -> Option<&'a Value> {
+    if let Some(value) = claims.get(path).filter(|v| !v.is_null()) {
+        return Some(value);
+    }
+    path.split('.')
+        .try_fold(claims, |value, key| value.get(key))
+        .filter(|v| !v.is_null())
+}
+
+#[cfg(feature = "jwt")]
+fn claim_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) if !s.is_empty() => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(all(test, feature = "jwt"))]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn config(toml_str: &str) -> BearerJwtConfig {
+        toml::from_str(&format!(
+            r#"
+            issuer = "https://auth.example.com/"
+            audience = "hadrian"
+            jwks_url = "https://auth.example.com/jwks.json"
+            {toml_str}
+            "#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_claim_paths() {
+        let claims = json!({
+            "sub": "svc-123",
+            "https://example.com/org": "acme",
+            "tenant": { "project": "billing", "id": 42 },
+            "empty": "",
+        });
+
+        assert_eq!(claim(&claims, "sub"), Some(&json!("svc-123")));
+        assert_eq!(
+            claim(&claims, "https://example.com/org"),
+            Some(&json!("acme"))
+        );
+        assert_eq!(claim(&claims, "tenant.project"), Some(&json!("billing")));
+        assert_eq!(claim(&claims, "tenant.missing"), None);
+        assert_eq!(
+            claim(&claims, "tenant.id").and_then(claim_string),
+            Some("42".into())
+        );
+        assert_eq!(claim(&claims, "empty").and_then(claim_string), None);
+    }
+
+    #[test]
+    fn test_map_claims() {
+        let config = config(
+            r#"
+            subject_claim = "client_id"
+            user_claim = "sub"
+            org_claim = "https://example.com/org"
+            project_claim = "tenant.project"
+            roles_claim = "realm_access.roles"
+            require_org = true
+            "#,
+        );
+        let claims = json!({
+            "sub": "user-1",
+            "client_id": "reporting-job",
+            "email": "job@example.com",
+            "https://example.com/org": "acme",
+            "tenant": { "project": "billing" },
+            "realm_access": { "roles": ["reader", "writer"] },
+        });
+
+        let mapped = map_claims(&config, &claims).unwrap();
+        assert_eq!(mapped.issuer, "https://auth.example.com/");
+        assert_eq!(mapped.subject, "reporting-job");
+        assert_eq!(mapped.user.as_deref(), Some("user-1"));
+        assert_eq!(mapped.org.as_deref(), Some("acme"));
+        assert_eq!(mapped.project.as_deref(), Some("billing"));
+        assert_eq!(mapped.roles, vec!["reader", "writer"]);
+        assert_eq!(mapped.email.as_deref(), Some("job@example.com"));
+        assert!(mapped.require_org);
+    }
+
+    #[test]
+    fn test_map_claims_requires_subject() {
+        let config = config(r#"subject_claim = "azp""#);
+        assert!(map_claims(&config, &json!({ "sub": "user-1" })).is_err());
+
+        let mapped = map_claims(&config, &json!({ "azp": "app", "roles": "admin" })).unwrap();
+        assert_eq!(mapped.subject, "app");
+        assert!(mapped.roles.is_empty());
+        assert!(mapped.org.is_none());
+    }
+}
@@ -0,0 +1,160 @@
+//! Client certificate (mTLS) authentication.
+//!
+//! When the gateway terminates TLS itself with `[server.tls.client_auth]`,
+//! the TLS listener verifies the presented certificate chain and attaches a
+//! [`ClientCertificate`] to every request on the connection. The API
+//! middleware then resolves the certificate's identifiers against the
+//! client certificate mappings; a match authenticates the request as the
+//! mapped service account ([`ClientCertAuth`]).
+
+use uuid::Uuid;
+
+use crate::models::CertMatchType;
+
+/// Identifiers extracted from a verified client certificate.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClientCertificate {
+    /// Subject common name, if present.
+    pub subject_cn: Option<String>,
+    /// `dNSName` subject alternative names.
+    pub san_dns: Vec<String>,
+    /// `uniformResourceIdentifier` subject alternative names (e.g. SPIFFE IDs).
+    pub san_uris: Vec<String>,
+    /// `rfc822Name` subject alternative names.
+    pub san_emails: Vec<String>,
+    /// Lowercase hex SHA-256 of the DER-encoded leaf certificate.
+    pub fingerprint_sha256: String,
+}
+
+impl ClientCertificate {
+    /// Parse the identifiers out of a DER-encoded leaf certificate.
+    ///
+    /// The chain must already have been verified by the TLS layer; this only
+    /// extracts fields for mapping lookup.
+    #[cfg(feature = "tls")]
+    pub fn from_der(der: &[u8]) -> Result<Self, String> {
+        use sha2::{Digest, Sha256};
+        use x509_parser::{extensions::GeneralName, prelude::parse_x509_certificate};
+
+        let (_, cert) =
+            parse_x509_certificate(der).map_err(|e| format!("invalid certificate: {e}"))?;
+
+        let subject_cn = cert
+            .subject()
+            .iter_common_name()
+            .next()
+            .and_then(|cn| cn.as_str().ok())
+            .map(str::to_string);
+
+        let mut parsed = Self {
+            subject_cn,
+            fingerprint_sha256: hex::encode(Sha256::digest(der)),
+            ..Default::default()
+        };
+
+        if let Ok(Some(san)) = cert.subject_alternative_name() {
+            for name in &san.value.general_names {
+                match name {
+                    GeneralName::DNSName(v) => parsed.san_dns.push(v.to_string()),
+                    GeneralName::URI(v) => parsed.san_uris.push(v.to_string()),
+                    GeneralName::RFC822Name(v) => parsed.san_emails.push(v.to_string()),
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    /// All `(match type, normalized value)` pairs this certificate can be
+    /// mapped by, in precedence order.
+    pub fn identifiers(&self) -> Vec<(CertMatchType, String)> {
+        let mut ids = vec![(
+            CertMatchType::FingerprintSha256,
+            CertMatchType::FingerprintSha256.normalize(&self.fingerprint_sha256),
+        )];
+        let fields = [
+            (CertMatchType::SanUri, &self.san_uris),
+            (CertMatchType::SanDns, &self.san_dns),
+            (CertMatchType::SanEmail, &self.san_emails),
+        ];
+        for (match_type, values) in fields {
+            ids.extend(values.iter().map(|v| (match_type, match_type.normalize(v))));
+        }
+        if let Some(cn) = &self.subject_cn {
+            ids.push((
+                CertMatchType::SubjectCn,
+                CertMatchType::SubjectCn.normalize(cn),
+            ));
+        }
+        ids.retain(|(_, v)| !v.is_empty());
+        ids
+    }
+}
+
+/// A request authenticated by a client certificate mapped to a service account.
+#[derive(Debug, Clone)]
+pub struct ClientCertAuth {
+    /// The mapping that matched.
+    pub mapping_id: Uuid,
+    pub service_account_id: Uuid,
+    pub org_id: Uuid,
+    /// Roles of the service account (pre-fetched for RBAC evaluation)
+    pub roles: Vec<String>,
+    /// Fingerprint of the presented certificate, for audit and logging.
+    pub fingerprint_sha256: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identifiers_are_normalized_and_ordered() {
+        let cert = ClientCertificate {
+            subject_cn: Some("billing".to_string()),
+            san_dns: vec!["Billing.Internal".to_string()],
+            san_uris: vec!["spiffe://example.org/billing".to_string()],
+            san_emails: vec![],
+            fingerprint_sha256: "AB:CD".to_string(),
+        };
+
+        assert_eq!(
+            cert.identifiers(),
+            vec![
+                (CertMatchType::FingerprintSha256, "abcd".to_string()),
+                (
+                    CertMatchType::SanUri,
+                    "spiffe://example.org/billing".to_string()
+                ),
+                (CertMatchType::SanDns, "billing.internal".to_string()),
+                (CertMatchType::SubjectCn, "billing".to_string()),
+            ]
+        );
+    }
+
+    #[cfg(feature = "tls")]
+    #[test]
+    fn test_from_der_extracts_subject_and_sans() {
+        use rcgen::{CertificateParams, DistinguishedName, DnType, KeyPair, SanType};
+
+        let mut params = CertificateParams::default();
+        let mut dn = DistinguishedName::new();
+        dn.push(DnType::CommonName, "ingest-worker");
+        params.distinguished_name = dn;
+        params.subject_alt_names = vec![
+            SanType::DnsName("ingest.internal".try_into().unwrap()),
+            SanType::URI("spiffe://example.org/ingest".try_into().unwrap()),
+            SanType::Rfc822Name("ingest@example.org".try_into().unwrap()),
+        ];
+        let key = KeyPair::generate().unwrap();
+        let cert = params.self_signed(&key).unwrap();
+
+        let parsed = ClientCertificate::from_der(cert.der()).unwrap();
+        assert_eq!(parsed.subject_cn.as_deref(), Some("ingest-worker"));
+        assert_eq!(parsed.san_dns, vec!["ingest.internal"]);
+        assert_eq!(parsed.san_uris, vec!["spiffe://example.org/ingest"]);
+        assert_eq!(parsed.san_emails, vec!["ingest@example.org"]);
+        assert_eq!(parsed.fingerprint_sha256.len(), 64);
+    }
+}
@@ -65,6 +65,12 @@ pub enum AuthError {
     /// API key does not allow requests from this IP address
     IPNotAllowed { ip: String, allowlist: Vec<String> },
 
+    /// High-risk admin operation needs a recent sign-in or TOTP verification
+    StepUpRequired { totp_enrolled: bool },
+
+    /// Admin session must be verified with a passkey (`[auth.admin] passkeys = "required"`)
+    PasskeyRequired { registered: bool },
+
     /// Internal error during authentication
     Internal(String),
 }
@@ -160,6 +166,31 @@ impl IntoResponse for AuthError {
                 let body = ErrorResponse::with_type("permission_error", "ip_not_allowed", message);
                 return (StatusCode::FORBIDDEN, Json(body)).into_response();
             }
+            AuthError::StepUpRequired { totp_enrolled } => {
+                metrics::record_gateway_error("auth_failure", "step_up_required", None);
+                let message = if *totp_enrolled {
+                    "This operation requires step-up authentication: verify a TOTP code \
+                     (POST /admin/v1/me/step-up/verify or the X-Step-Up-TOTP header)"
+                } else {
+                    "This operation requires step-up authentication: sign in again, or \
+                     enroll a TOTP authenticator at /admin/v1/me/step-up/totp/enroll"
+                };
+                let body =
+                    ErrorResponse::with_type("permission_error", "step_up_required", message);
+                return (StatusCode::FORBIDDEN, Json(body)).into_response();
+            }
+            AuthError::PasskeyRequired { registered } => {
+                metrics::record_gateway_error("auth_failure", "passkey_required", None);
+                let message = if *registered {
+                    "This session must be verified with a passkey \
+                     (POST /auth/webauthn/authenticate/start)"
+                } else {
+                    "A passkey is required: register one at /auth/webauthn/register/start"
+                };
+                let body =
+                    ErrorResponse::with_type("permission_error", "passkey_required", message);
+                return (StatusCode::FORBIDDEN, Json(body)).into_response();
+            }
             AuthError::Internal(msg) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "internal_error",
@@ -229,6 +260,15 @@ impl fmt::Display for AuthError {
                     allowlist.join(", ")
                 )
             }
+            AuthError::StepUpRequired { totp_enrolled } => {
+                write!(
+                    f,
+                    "Step-up authentication required (TOTP enrolled: {totp_enrolled})"
+                )
+            }
+            AuthError::PasskeyRequired { registered } => {
+                write!(f, "Passkey verification required (registered: {registered})")
+            }
             AuthError::Internal(msg) => write!(f, "Internal error: {}", msg),
         }
     }
@@ -269,4 +309,20 @@ mod tests {
         let response = error.into_response();
         assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
     }
+
+    #[test]
+    fn test_step_up_required_is_403() {
+        let error = AuthError::StepUpRequired {
+            totp_enrolled: true,
+        };
+        let response = error.into_response();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn test_passkey_required_is_403() {
+        let error = AuthError::PasskeyRequired { registered: false };
+        let response = error.into_response();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
 }
@@ -0,0 +1,362 @@
+//! Short-lived tokens minted from API keys.
+//!
+//! `POST /api/v1/auth/token` trades a long-lived API key for an HS256 JWT
+//! signed with `[auth.token_exchange].signing_key`. A token can only narrow
+//! what its key allows: fewer models, fewer scopes, or one project. The API
+//! middleware accepts it in place of the key, so budgets, rate limits and IP
+//! allowlists still apply to the key, and revoking the key invalidates its
+//! tokens.
+
+use chrono::{TimeZone, Utc};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation, decode, encode};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{ApiKeyAuth, AuthError, ExchangedToken};
+use crate::{
+    config::TokenExchangeConfig,
+    models::{ApiKey, model_matches_pattern},
+};
+
+/// `aud` claim of exchanged tokens, so they can't be confused with other
+/// JWTs signed by an operator reusing the key.
+pub const EXCHANGE_TOKEN_AUDIENCE: &str = "hadrian:api";
+
+/// Claims of an exchanged token.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExchangeTokenClaims {
+    pub iss: String,
+    /// ID of the API key the token was minted from
+    pub sub: Uuid,
+    pub aud: String,
+    pub iat: i64,
+    pub exp: i64,
+    pub jti: Uuid,
+    /// Owner context of the key, fixed when the token is minted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<Uuid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub team_id: Option<Uuid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<Uuid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<Uuid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service_account_id: Option<Uuid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service_account_roles: Option<Vec<String>>,
+    /// Scopes the token carries (`None` = the key's scopes)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scopes: Option<Vec<String>>,
+    /// Model patterns the token allows (`None` = the key's models)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub models: Option<Vec<String>>,
+}
+
+/// Restrictions a client asks for when exchanging a key.
+#[derive(Debug, Clone, Default)]
+pub struct TokenRestrictions {
+    pub models: Option<Vec<String>>,
+    pub scopes: Option<Vec<String>>,
+    /// Project the token is bound to, already resolved and checked against
+    /// the key's owner
+    pub project_id: Option<Uuid>,
+}
+
+/// Signs and verifies exchanged tokens.
+pub struct ExchangeTokenSigner {
+    issuer: String,
+    encoding: EncodingKey,
+    decoding: DecodingKey,
+}
+
+impl ExchangeTokenSigner {
+    /// Build a signer when token exchange is enabled and configured.
+    pub fn from_config(config: &TokenExchangeConfig) -> Option<Self> {
+        let key = config.signing_key.as_deref().filter(|_| config.enabled)?;
+        Some(Self {
+            issuer: config.issuer.clone(),
+            encoding: EncodingKey::from_secret(key.as_bytes()),
+            decoding: DecodingKey::from_secret(key.as_bytes()),
+        })
+    }
+
+    pub fn issuer(&self) -> &str {
+        &self.issuer
+    }
+
+    /// Mint a token for `key` lasting `ttl_secs`. `restrictions` must already
+    /// be checked with [`narrow`].
+    pub fn mint(
+        &self,
+        key: &ApiKeyAuth,
+        restrictions: TokenRestrictions,
+        ttl_secs: u64,
+    ) -> Result<(String, ExchangeTokenClaims), AuthError> {
+        let now = Utc::now().timestamp();
+        let claims = ExchangeTokenClaims {
+            iss: self.issuer.clone(),
+            sub: key.key.id,
+            aud: EXCHANGE_TOKEN_AUDIENCE.to_string(),
+            iat: now,
+            exp: now + ttl_secs as i64,
+            jti: Uuid::new_v4(),
+            org_id: key.org_id,
+            team_id: key.team_id,
+            project_id: restrictions.project_id.or(key.project_id),
+            user_id: key.user_id,
+            service_account_id: key.service_account_id,
+            service_account_roles: key.service_account_roles.clone(),
+            scopes: restrictions.scopes,
+            models: restrictions.models,
+        };
+        let token = encode(&Header::new(Algorithm::HS256), &claims, &self.encoding)
+            .map_err(|e| AuthError::Internal(format!("Failed to sign token: {e}")))?;
+        Ok((token, claims))
+    }
+
+    /// Verify a token's signature, issuer, audience and expiry.
+    pub fn verify(&self, token: &str) -> Result<ExchangeTokenClaims, AuthError> {
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.set_issuer(&[&self.issuer]);
+        validation.set_audience(&[EXCHANGE_TOKEN_AUDIENCE]);
+        validation.leeway = 0;
+
+        decode::<ExchangeTokenClaims>(token, &self.decoding, &validation)
+            .map(|data| data.claims)
+            .map_err(|e| match e.kind() {
+                jsonwebtoken::errors::ErrorKind::ExpiredSignature => AuthError::ExpiredToken,
+                _ => {
+                    tracing::debug!(error = %e, "Exchanged token rejected");
+                    AuthError::InvalidToken
+                }
+            })
+    }
+}
+
+/// Check that requested models and scopes are within what `key` allows.
+///
+/// Returns a client-facing message naming the first entry that isn't.
+pub fn narrow(
+    key: &ApiKey,
+    models: Option<&[String]>,
+    scopes: Option<&[String]>,
+) -> Result<(), String> {
+    if let Some(models) = models {
+        if models.is_empty() {
+            return Err("models must not be empty; omit it to keep the key's models".into());
+        }
+        // A requested pattern is allowed when a key pattern covers it as a
+        // whole, e.g. key `gpt-4*` covers `gpt-4o` and `gpt-4o*`.
+        let covered = |model: &str| match key.allowed_models.as_deref() {
+            None | Some([]) => true,
+            Some(patterns) => patterns.iter().any(|p| model_matches_pattern(model, p)),
+        };
+        if let Some(model) = models.iter().find(|m| !covered(m)) {
+            return Err(format!("Model '{model}' is not allowed by this API key"));
+        }
+    }
+
+    if let Some(scopes) = scopes {
+        if scopes.is_empty() {
+            return Err("scopes must not be empty; omit it to keep the key's scopes".into());
+        }
+        if let Err(invalid) = crate::models::validate_scopes(scopes) {
+            return Err(format!("Invalid scopes: {}", invalid.join(", ")));
+        }
+        if let Some(granted) = &key.scopes
+            && let Some(scope) = scopes.iter().find(|s| !scope_covered(s, granted))
+        {
+            return Err(format!("Scope '{scope}' is not granted to this API key"));
+        }
+    }
+    Ok(())
+}
+
+/// Whether `requested` is no broader than one of the `granted` scopes:
+/// `chat:write` is covered by `chat`, `admin:usage:read` by `admin:usage`.
+fn scope_covered(requested: &str, granted: &[String]) -> bool {
+    granted.iter().any(|g| {
+        requested == g
+            || requested
+                .strip_prefix(g.as_str())
+                .is_some_and(|rest| rest.starts_with(':'))
+    })
+}
+
+/// Rebuild the key authentication an exchanged token stands for.
+///
+/// `key` is the freshly loaded API key; the token's restrictions replace
+/// its scopes and models, and its owner context comes from the claims.
+pub fn apply(key: ApiKey, claims: ExchangeTokenClaims) -> ApiKeyAuth {
+    let mut key = key;
+    if claims.scopes.is_some() {
+        key.scopes = claims.scopes;
+    }
+    if claims.models.is_some() {
+        key.allowed_models = claims.models;
+    }
+    ApiKeyAuth {
+        key,
+        org_id: claims.org_id,
+        team_id: claims.team_id,
+        project_id: claims.project_id,
+        user_id: claims.user_id,
+        service_account_id: claims.service_account_id,
+        service_account_roles: claims.service_account_roles,
+        exchanged: Some(ExchangedToken {
+            jti: claims.jti,
+            expires_at: Utc
+                .timestamp_opt(claims.exp, 0)
+                .single()
+                .unwrap_or_default(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ApiKeyOwner;
+
+    fn test_key(scopes: Option<&[&str]>, models: Option<&[&str]>) -> ApiKey {
+        ApiKey {
+            id: Uuid::new_v4(),
+            key_prefix: "gw_test".to_string(),
+            name: "test".to_string(),
+            owner: ApiKeyOwner::Organization {
+                org_id: Uuid::new_v4(),
+            },
+            budget_limit_cents: None,
+            budget_period: None,
+            created_at: Utc::now(),
+            expires_at: None,
+            revoked_at: None,
+            last_used_at: None,
+            scopes: scopes.map(|s| s.iter().map(|s| s.to_string()).collect()),
+            allowed_models: models.map(|m| m.iter().map(|m| m.to_string()).collect()),
+            ip_allowlist: None,
+            rate_limit_rpm: None,
+            rate_limit_tpm: None,
+            rotated_from_key_id: None,
+            rotation_grace_until: None,
+            sovereignty_requirements: None,
+            labels: Default::default(),
+        }
+    }
+
+    fn signer() -> ExchangeTokenSigner {
+        ExchangeTokenSigner::from_config(&TokenExchangeConfig {
+            enabled: true,
+            signing_key: Some("0123456789abcdef0123456789abcdef".to_string()),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    fn auth(key: ApiKey) -> ApiKeyAuth {
+        ApiKeyAuth {
+            org_id: Some(Uuid::new_v4()),
+            team_id: None,
+            project_id: None,
+            user_id: None,
+            service_account_id: None,
+            service_account_roles: None,
+            exchanged: None,
+            key,
+        }
+    }
+
+    #[test]
+    fn test_signer_requires_enabled_and_key() {
+        assert!(ExchangeTokenSigner::from_config(&TokenExchangeConfig::default()).is_none());
+        assert!(
+            ExchangeTokenSigner::from_config(&TokenExchangeConfig {
+                enabled: true,
+                ..Default::default()
+            })
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_mint_and_verify_round_trip() {
+        let signer = signer();
+        let parent = auth(test_key(None, None));
+        let project_id = Uuid::new_v4();
+        let (token, claims) = signer
+            .mint(
+                &parent,
+                TokenRestrictions {
+                    models: Some(vec!["gpt-4o-mini".into()]),
+                    scopes: Some(vec!["chat".into()]),
+                    project_id: Some(project_id),
+                },
+                900,
+            )
+            .unwrap();
+
+        let verified = signer.verify(&token).unwrap();
+        assert_eq!(verified, claims);
+        assert_eq!(verified.sub, parent.key.id);
+        assert_eq!(verified.project_id, Some(project_id));
+        assert_eq!(verified.exp - verified.iat, 900);
+
+        let restored = apply(parent.key.clone(), verified);
+        assert_eq!(restored.project_id, Some(project_id));
+        assert!(restored.key.is_model_allowed("gpt-4o-mini"));
+        assert!(!restored.key.is_model_allowed("gpt-4o"));
+        assert!(restored.exchanged.is_some());
+    }
+
+    #[test]
+    fn test_verify_rejects_other_signers_and_expired_tokens() {
+        let parent = auth(test_key(None, None));
+        let (token, _) = signer()
+            .mint(&parent, TokenRestrictions::default(), 60)
+            .unwrap();
+
+        let other = ExchangeTokenSigner::from_config(&TokenExchangeConfig {
+            enabled: true,
+            signing_key: Some("fedcba9876543210fedcba9876543210".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(matches!(other.verify(&token), Err(AuthError::InvalidToken)));
+
+        let mut claims = signer().verify(&token).unwrap();
+        claims.exp = Utc::now().timestamp() - 10;
+        let expired = encode(&Header::new(Algorithm::HS256), &claims, &signer().encoding).unwrap();
+        assert!(matches!(
+            signer().verify(&expired),
+            Err(AuthError::ExpiredToken)
+        ));
+    }
+
+    #[test]
+    fn test_narrow_models() {
+        let key = test_key(None, Some(&["gpt-4*", "claude-3-haiku"]));
+        assert!(narrow(&key, Some(&["gpt-4o".into()]), None).is_ok());
+        assert!(narrow(&key, Some(&["gpt-4o*".into()]), None).is_ok());
+        assert!(narrow(&key, Some(&["claude-3-haiku".into()]), None).is_ok());
+        assert!(narrow(&key, Some(&["claude-3-opus".into()]), None).is_err());
+        assert!(narrow(&key, Some(&["*".into()]), None).is_err());
+        assert!(narrow(&key, Some(&[]), None).is_err());
+
+        let unrestricted = test_key(None, None);
+        assert!(narrow(&unrestricted, Some(&["anything".into()]), None).is_ok());
+    }
+
+    #[test]
+    fn test_narrow_scopes() {
+        let key = test_key(Some(&["chat", "models:read"]), None);
+        assert!(narrow(&key, None, Some(&["chat:write".into()])).is_ok());
+        assert!(narrow(&key, None, Some(&["models:read".into()])).is_ok());
+        assert!(narrow(&key, None, Some(&["models".into()])).is_err());
+        assert!(narrow(&key, None, Some(&["embeddings".into()])).is_err());
+        assert!(narrow(&key, None, Some(&["bogus".into()])).is_err());
+
+        let unrestricted = test_key(None, None);
+        assert!(narrow(&unrestricted, None, Some(&["admin:usage:read".into()])).is_ok());
+    }
+}
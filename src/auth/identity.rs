@@ -2,7 +2,7 @@ use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
 use super::{
-    AuthError,
+    AuthError, BearerJwtAuth, ClientCertAuth,
     principal::{Principal, derive_principal},
 };
 use crate::{
@@ -57,6 +57,12 @@ pub enum IdentityKind {
         api_key: Box<ApiKeyAuth>,
         identity: Identity,
     },
+
+    /// Authenticated via a client certificate mapped to a service account (mTLS)
+    ClientCert(Box<ClientCertAuth>),
+
+    /// Authenticated via a JWT from an external issuer configured in `[[auth.jwt]]`
+    BearerJwt(Box<BearerJwtAuth>),
 }
 
 /// API key authentication details
@@ -70,6 +76,17 @@ pub struct ApiKeyAuth {
     pub service_account_id: Option<Uuid>,
     /// Roles from the service account (pre-fetched for RBAC evaluation)
     pub service_account_roles: Option<Vec<String>>,
+    /// Set when the request used a short-lived token minted from this key
+    /// (`POST /api/v1/auth/token`) instead of the key itself
+    pub exchanged: Option<ExchangedToken>,
+}
+
+/// Marks an [`ApiKeyAuth`] as coming from an exchanged token rather than the
+/// key itself.
+#[derive(Debug, Clone)]
+pub struct ExchangedToken {
+    pub jti: Uuid,
+    pub expires_at: DateTime<Utc>,
 }
 
 impl ApiKeyAuth {
@@ -174,19 +191,64 @@ impl AuthenticatedRequest {
             IdentityKind::ApiKey(key) => key.user_id,
             IdentityKind::Identity(id) => id.user_id,
             IdentityKind::Both { identity, .. } => identity.user_id,
+            IdentityKind::ClientCert(_) => None,
+            IdentityKind::BearerJwt(jwt) => jwt.user_id,
         }
     }
 
-    /// Get the org ID from the API key
-    #[allow(dead_code)] // Public API for CEL evaluation
+    /// Get the org ID from the API key, client certificate, or bearer JWT
     pub fn org_id(&self) -> Option<Uuid> {
-        self.api_key().and_then(|k| k.org_id)
+        match &self.kind {
+            IdentityKind::ClientCert(cert) => Some(cert.org_id),
+            IdentityKind::BearerJwt(jwt) => jwt.org_id,
+            _ => self.api_key().and_then(|k| k.org_id),
+        }
+    }
+
+    /// The organization the request is attributed to: the credential's org
+    /// (see [`Self::org_id`]), or else the first org of the session identity.
+    pub fn effective_org_id(&self) -> Option<Uuid> {
+        self.org_id().or_else(|| {
+            self.identity()
+                .and_then(|i| i.org_ids.first())
+                .and_then(|id| Uuid::parse_str(id).ok())
+        })
+    }
+
+    /// Get the client certificate auth if available
+    pub fn client_cert(&self) -> Option<&ClientCertAuth> {
+        match &self.kind {
+            IdentityKind::ClientCert(cert) => Some(cert),
+            _ => None,
+        }
+    }
+
+    /// Get the external JWT auth if available
+    pub fn bearer_jwt(&self) -> Option<&BearerJwtAuth> {
+        match &self.kind {
+            IdentityKind::BearerJwt(jwt) => Some(jwt),
+            _ => None,
+        }
     }
 
-    /// Get the project ID from the API key
+    /// Get the project ID from the API key or bearer JWT
     #[allow(dead_code)] // Public API for CEL evaluation
     pub fn project_id(&self) -> Option<Uuid> {
-        self.api_key().and_then(|k| k.project_id)
+        match &self.kind {
+            IdentityKind::BearerJwt(jwt) => jwt.project_id,
+            _ => self.api_key().and_then(|k| k.project_id),
+        }
+    }
+
+    /// The project the request is attributed to: the credential's project
+    /// (see [`Self::project_id`]), or else the first project of the session
+    /// identity.
+    pub fn effective_project_id(&self) -> Option<Uuid> {
+        self.project_id().or_else(|| {
+            self.identity()
+                .and_then(|i| i.project_ids.first())
+                .and_then(|id| Uuid::parse_str(id).ok())
+        })
     }
 
     /// Get the Principal for this authenticated request.
@@ -1,7 +1,11 @@
+mod bearer_jwt;
+mod client_cert;
 #[cfg(feature = "sso")]
 mod discovery;
 mod error;
 #[cfg(feature = "jwt")]
+pub mod exchange_token;
+#[cfg(feature = "jwt")]
 pub mod gateway_jwt;
 mod identity;
 #[cfg(feature = "jwt")]
@@ -17,13 +21,22 @@ pub mod saml;
 mod saml_registry;
 #[cfg(feature = "sso")]
 pub mod session_store;
+pub mod totp;
+#[cfg(feature = "webauthn")]
+pub mod webauthn;
 
+pub use bearer_jwt::BearerJwtAuth;
+#[cfg(feature = "jwt")]
+pub use bearer_jwt::{BearerJwtAuthenticator, BearerJwtClaims, bearer_jwt_external_id};
+pub use client_cert::{ClientCertAuth, ClientCertificate};
 #[cfg(feature = "sso")]
 pub use discovery::fetch_jwks_uri;
 pub use error::AuthError;
 #[cfg(feature = "jwt")]
+pub use exchange_token::ExchangeTokenSigner;
+#[cfg(feature = "jwt")]
 pub use gateway_jwt::GatewayJwtRegistry;
-pub use identity::{ApiKeyAuth, AuthenticatedRequest, Identity, IdentityKind};
+pub use identity::{ApiKeyAuth, AuthenticatedRequest, ExchangedToken, Identity, IdentityKind};
 #[cfg(feature = "sso")]
 pub use oidc::OidcAuthenticator;
 #[cfg(feature = "sso")]
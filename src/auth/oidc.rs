@@ -8,6 +8,7 @@
 //! - Token refresh (if refresh tokens are available)
 
 use std::{
+    collections::HashMap,
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -25,7 +26,7 @@ use super::{
     jwt::JwtValidator,
     session_store::{
         AuthorizationState, DeviceInfo, MemorySessionStore, OidcSession, SharedSessionStore,
-        enforce_session_limit, validate_and_refresh_session,
+        validate_and_refresh_session,
     },
 };
 use crate::{
@@ -495,6 +496,7 @@ impl OidcAuthenticator {
             crate::middleware::strip_reserved_roles(claims.roles.clone().unwrap_or_default());
         let groups =
             crate::middleware::strip_reserved_roles(claims.groups.clone().unwrap_or_default());
+        let identity_claims = session_claims(&claims, &groups, &roles);
 
         let session = OidcSession {
             id: Uuid::new_v4(),
@@ -513,6 +515,8 @@ impl OidcAuthenticator {
             session_index: None, // OIDC doesn't use session_index (SAML only)
             device: device_info,
             last_activity: Some(now),
+            claims: identity_claims,
+            passkey_verified_at: None,
         };
 
         // Store session
@@ -521,25 +525,8 @@ impl OidcAuthenticator {
             .await
             .map_err(|e| AuthError::Internal(format!("Failed to store session: {}", e)))?;
 
-        // Enforce concurrent session limit (Phase 2)
-        let enhanced = &self.config.session.enhanced;
-        if enhanced.enabled
-            && enhanced.max_concurrent_sessions > 0
-            && let Err(e) = enforce_session_limit(
-                self.session_store.as_ref(),
-                &session.external_id,
-                enhanced.max_concurrent_sessions,
-            )
-            .await
-        {
-            // Non-fatal: log but don't fail the login
-            tracing::warn!(
-                external_id = %session.external_id,
-                error = %e,
-                "Failed to enforce session limit"
-            );
-        }
-
+        // The concurrent session limit is enforced by the callback route, which
+        // can audit the evicted sessions.
         Ok((session, auth_state.return_to))
     }
 
@@ -675,6 +662,37 @@ pub async fn fetch_jwks_uri(
     super::fetch_jwks_uri(discovery_url, http_client, allow_loopback, allow_private).await
 }
 
+/// Claims kept on the session for claim-based role rules.
+///
+/// Token-binding claims are dropped; groups and roles use the sanitized
+/// values so rules can't match on reserved roles.
+fn session_claims(
+    claims: &super::jwt::JwtClaims,
+    groups: &[String],
+    roles: &[String],
+) -> HashMap<String, serde_json::Value> {
+    let mut map: HashMap<String, serde_json::Value> = claims
+        .extra
+        .iter()
+        .filter(|(k, _)| !matches!(k.as_str(), "nonce" | "at_hash" | "c_hash"))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    map.insert("sub".to_string(), claims.sub.clone().into());
+    map.insert("iss".to_string(), claims.iss.clone().into());
+    if let Some(email) = &claims.email {
+        map.insert("email".to_string(), email.clone().into());
+    }
+    if let Some(name) = &claims.name {
+        map.insert("name".to_string(), name.clone().into());
+    }
+    if let Some(org) = &claims.org {
+        map.insert("org".to_string(), org.clone().into());
+    }
+    map.insert("groups".to_string(), groups.into());
+    map.insert("roles".to_string(), roles.into());
+    map
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::json;
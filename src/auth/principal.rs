@@ -29,6 +29,12 @@
 //! Both (API key + Identity):
 //!   - SA owner → ServiceAccount principal (SA takes precedence)
 //!   - Otherwise → User principal (identity provides fields)
+//!
+//! Client certificate (mTLS):
+//!   - Always ServiceAccount principal (the mapped service account)
+//!
+//! External bearer JWT (`[[auth.jwt]]`):
+//!   - Always User principal (subject as external ID, org/project from claims)
 //! ```
 
 use serde::{Deserialize, Serialize};
@@ -393,6 +399,23 @@ pub fn derive_principal(auth: &AuthenticatedRequest) -> Principal {
             }
         }
 
+        IdentityKind::ClientCert(cert) => Principal::ServiceAccount {
+            id: cert.service_account_id,
+            org_id: cert.org_id,
+            roles: cert.roles.clone(),
+        },
+
+        IdentityKind::BearerJwt(jwt) => Principal::User {
+            user_id: jwt.user_id,
+            external_id: Some(jwt.subject.clone()),
+            email: jwt.email.clone(),
+            name: jwt.name.clone(),
+            roles: jwt.roles.clone(),
+            org_ids: jwt.org_id.iter().map(Uuid::to_string).collect(),
+            team_ids: Vec::new(),
+            project_ids: jwt.project_id.iter().map(Uuid::to_string).collect(),
+        },
+
         IdentityKind::Identity(identity) => {
             // Pure identity auth (OIDC/SAML/proxy)
             Principal::User {
@@ -481,7 +504,7 @@ mod tests {
 
     use super::*;
     use crate::{
-        auth::{ApiKeyAuth, Identity},
+        auth::{ApiKeyAuth, BearerJwtAuth, ClientCertAuth, Identity},
         models::ApiKey,
     };
 
@@ -505,6 +528,7 @@ mod tests {
             rotated_from_key_id: None,
             rotation_grace_until: None,
             sovereignty_requirements: None,
+            labels: Default::default(),
         }
     }
 
@@ -523,6 +547,7 @@ mod tests {
             user_id: None,
             service_account_id: Some(sa_id),
             service_account_roles: Some(vec!["deployer".to_string(), "viewer".to_string()]),
+            exchanged: None,
         };
 
         let auth = AuthenticatedRequest::new(IdentityKind::ApiKey(Box::new(api_key_auth)));
@@ -554,6 +579,7 @@ mod tests {
             user_id: Some(user_id),
             service_account_id: None,
             service_account_roles: None,
+            exchanged: None,
         };
 
         let auth = AuthenticatedRequest::new(IdentityKind::ApiKey(Box::new(api_key_auth)));
@@ -584,6 +610,7 @@ mod tests {
             user_id: None,
             service_account_id: None,
             service_account_roles: None,
+            exchanged: None,
         };
 
         let auth = AuthenticatedRequest::new(IdentityKind::ApiKey(Box::new(api_key_auth)));
@@ -612,6 +639,7 @@ mod tests {
             user_id: None,
             service_account_id: None,
             service_account_roles: None,
+            exchanged: None,
         };
 
         let auth = AuthenticatedRequest::new(IdentityKind::ApiKey(Box::new(api_key_auth)));
@@ -645,6 +673,7 @@ mod tests {
             user_id: None,
             service_account_id: None,
             service_account_roles: None,
+            exchanged: None,
         };
 
         let auth = AuthenticatedRequest::new(IdentityKind::ApiKey(Box::new(api_key_auth)));
@@ -721,6 +750,7 @@ mod tests {
             user_id: None,
             service_account_id: Some(sa_id),
             service_account_roles: Some(vec!["sa_role".to_string()]),
+            exchanged: None,
         };
 
         let identity = Identity {
@@ -764,6 +794,7 @@ mod tests {
             user_id: None,
             service_account_id: None,
             service_account_roles: None,
+            exchanged: None,
         };
 
         let identity = Identity {
@@ -802,6 +833,71 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_derive_principal_client_cert() {
+        let sa_id = Uuid::new_v4();
+        let org_id = Uuid::new_v4();
+
+        let auth = AuthenticatedRequest::new(IdentityKind::ClientCert(Box::new(ClientCertAuth {
+            mapping_id: Uuid::new_v4(),
+            service_account_id: sa_id,
+            org_id,
+            roles: vec!["ingest".to_string()],
+            fingerprint_sha256: "ab".repeat(32),
+        })));
+
+        assert_eq!(auth.org_id(), Some(org_id));
+        assert_eq!(auth.user_id(), None);
+        match derive_principal(&auth) {
+            Principal::ServiceAccount {
+                id,
+                org_id: o,
+                roles,
+            } => {
+                assert_eq!(id, sa_id);
+                assert_eq!(o, org_id);
+                assert_eq!(roles, vec!["ingest"]);
+            }
+            _ => panic!("Expected ServiceAccount principal"),
+        }
+    }
+
+    #[test]
+    fn test_derive_principal_bearer_jwt() {
+        let org_id = Uuid::new_v4();
+        let project_id = Uuid::new_v4();
+
+        let auth = AuthenticatedRequest::new(IdentityKind::BearerJwt(Box::new(BearerJwtAuth {
+            issuer: "https://auth.example.com/".to_string(),
+            subject: "reporting-job".to_string(),
+            user_id: None,
+            org_id: Some(org_id),
+            project_id: Some(project_id),
+            roles: vec!["reader".to_string()],
+            email: None,
+            name: None,
+        })));
+
+        assert_eq!(auth.org_id(), Some(org_id));
+        assert_eq!(auth.project_id(), Some(project_id));
+        assert_eq!(auth.user_id(), None);
+        match derive_principal(&auth) {
+            Principal::User {
+                external_id,
+                roles,
+                org_ids,
+                project_ids,
+                ..
+            } => {
+                assert_eq!(external_id.as_deref(), Some("reporting-job"));
+                assert_eq!(roles, vec!["reader"]);
+                assert_eq!(org_ids, vec![org_id.to_string()]);
+                assert_eq!(project_ids, vec![project_id.to_string()]);
+            }
+            _ => panic!("Expected User principal"),
+        }
+    }
+
     #[test]
     fn test_principal_to_subject_user() {
         let user_id = Uuid::new_v4();
@@ -8,7 +8,10 @@
 //! - Attribute extraction from assertions
 //! - Session management via cookies
 
-use std::time::{Duration, Instant};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
 
 use base64::{Engine, engine::general_purpose::STANDARD};
 use chrono::Utc;
@@ -24,7 +27,7 @@ use uuid::Uuid;
 use super::{
     AuthError,
     session_store::{
-        AuthorizationState, OidcSession, SharedSessionStore, enforce_session_limit,
+        AuthorizationState, DeviceInfo, OidcSession, SharedSessionStore,
         validate_and_refresh_session,
     },
 };
@@ -305,6 +308,19 @@ impl SamlAuthenticator {
         &self,
         saml_response: &str,
         relay_state: &str,
+    ) -> Result<(OidcSession, Option<String>), AuthError> {
+        self.exchange_response_with_device(saml_response, relay_state, None)
+            .await
+    }
+
+    /// Parse and validate a SAML Response, recording device info on the session.
+    ///
+    /// Returns the session and the optional `return_to` URL from the original login request.
+    pub async fn exchange_response_with_device(
+        &self,
+        saml_response: &str,
+        relay_state: &str,
+        device_info: Option<DeviceInfo>,
     ) -> Result<(OidcSession, Option<String>), AuthError> {
         // Verify and retrieve the auth state
         let auth_state = self
@@ -352,8 +368,10 @@ impl SamlAuthenticator {
             token_expires_at: None,
             sso_org_id: auth_state.org_id,
             session_index: assertion.session_index,
-            device: None, // Device info set by route handler
+            device: device_info,
             last_activity: Some(now),
+            claims: assertion.attributes,
+            passkey_verified_at: None,
         };
 
         // Store session
@@ -362,25 +380,8 @@ impl SamlAuthenticator {
             .await
             .map_err(|e| AuthError::Internal(format!("Failed to store session: {}", e)))?;
 
-        // Enforce concurrent session limit (Phase 2)
-        let enhanced = &self.config.session.enhanced;
-        if enhanced.enabled
-            && enhanced.max_concurrent_sessions > 0
-            && let Err(e) = enforce_session_limit(
-                self.session_store.as_ref(),
-                &session.external_id,
-                enhanced.max_concurrent_sessions,
-            )
-            .await
-        {
-            // Non-fatal: log but don't fail the login
-            tracing::warn!(
-                external_id = %session.external_id,
-                error = %e,
-                "Failed to enforce SAML session limit"
-            );
-        }
-
+        // The concurrent session limit is enforced by the ACS route, which can
+        // audit the evicted sessions.
         Ok((session, auth_state.return_to))
     }
 
@@ -462,6 +463,7 @@ impl SamlAuthenticator {
             name,
             groups,
             session_index,
+            attributes: collect_attributes(&assertion),
         })
     }
 
@@ -850,6 +852,38 @@ struct SamlAssertionData {
     groups: Vec<String>,
     /// SessionIndex from AuthnStatement (for SLO)
     session_index: Option<String>,
+    /// All attributes, keyed by name (and friendly name), for role rules
+    attributes: HashMap<String, serde_json::Value>,
+}
+
+/// Collect every attribute in the assertion as a claims map.
+///
+/// Single-valued attributes become strings and multi-valued ones arrays.
+/// Attributes are reachable by both their name and friendly name.
+fn collect_attributes(assertion: &samael::schema::Assertion) -> HashMap<String, serde_json::Value> {
+    let mut attributes = HashMap::new();
+    for statement in assertion.attribute_statements.iter().flatten() {
+        for attr in &statement.attributes {
+            let mut values: Vec<serde_json::Value> = attr
+                .values
+                .iter()
+                .filter_map(|v| v.value.clone())
+                .map(serde_json::Value::String)
+                .collect();
+            let value = if values.len() == 1 {
+                values.remove(0)
+            } else {
+                serde_json::Value::Array(values)
+            };
+            for key in [attr.name.as_ref(), attr.friendly_name.as_ref()]
+                .into_iter()
+                .flatten()
+            {
+                attributes.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    attributes
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
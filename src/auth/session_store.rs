@@ -165,6 +165,16 @@ pub struct OidcSession {
     /// Updated on session access when enhanced sessions are enabled
     #[serde(default)]
     pub last_activity: Option<DateTime<Utc>>,
+
+    /// Identity claims (OIDC) or attributes (SAML) captured at login.
+    /// Evaluated against the org's claim-based role rules during JIT provisioning.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub claims: HashMap<String, serde_json::Value>,
+
+    /// When a passkey was last verified for this session, either by signing
+    /// in with it or as a second factor on top of SSO.
+    #[serde(default)]
+    pub passkey_verified_at: Option<DateTime<Utc>>,
 }
 
 impl OidcSession {
@@ -850,7 +860,7 @@ pub fn create_session_store_with_enhanced(
 /// * `max_sessions` - Maximum allowed concurrent sessions (0 = unlimited)
 ///
 /// # Returns
-/// * `Ok(count)` - Number of sessions evicted
+/// * `Ok(sessions)` - The sessions that were evicted, so callers can audit them
 /// * `Err(SessionError)` - If an error occurred during eviction
 ///
 /// # Notes
@@ -861,16 +871,16 @@ pub async fn enforce_session_limit(
     session_store: &dyn SessionStore,
     external_id: &str,
     max_sessions: u32,
-) -> SessionResult<usize> {
+) -> SessionResult<Vec<OidcSession>> {
     // Skip if enhanced sessions not enabled or no limit set
     if !session_store.is_enhanced_enabled() || max_sessions == 0 {
-        return Ok(0);
+        return Ok(Vec::new());
     }
 
     // Check current session count
     let count = session_store.count_user_sessions(external_id).await?;
     if count <= max_sessions as usize {
-        return Ok(0);
+        return Ok(Vec::new());
     }
 
     // Need to evict some sessions
@@ -879,25 +889,26 @@ pub async fn enforce_session_limit(
     // Sort by created_at (oldest first)
     sessions.sort_by_key(|s| s.created_at);
 
-    let to_evict = count - max_sessions as usize;
-    let mut evicted = 0;
+    // The index count can include stale entries that listing just pruned
+    let to_evict = sessions.len().saturating_sub(max_sessions as usize);
+    let mut evicted = Vec::with_capacity(to_evict);
 
     for session in sessions.into_iter().take(to_evict) {
         if session_store.delete_session(session.id).await.is_ok() {
-            evicted += 1;
             tracing::info!(
                 session_id = %session.id,
                 external_id = %external_id,
                 created_at = %session.created_at,
                 "Evicted session due to concurrent session limit"
             );
+            evicted.push(session);
         }
     }
 
-    if evicted > 0 {
+    if !evicted.is_empty() {
         tracing::info!(
             external_id = %external_id,
-            evicted = evicted,
+            evicted = evicted.len(),
             max_sessions = max_sessions,
             "Enforced concurrent session limit"
         );
@@ -906,6 +917,26 @@ pub async fn enforce_session_limit(
     Ok(evicted)
 }
 
+/// Revoke every session a user holds.
+///
+/// Unlike [`SessionStore::delete_user_sessions`] this returns the revoked
+/// sessions, so callers can audit each one. Returns an empty list if
+/// enhanced sessions are not enabled.
+pub async fn revoke_user_sessions(
+    session_store: &dyn SessionStore,
+    external_id: &str,
+) -> SessionResult<Vec<OidcSession>> {
+    if !session_store.is_enhanced_enabled() {
+        return Ok(Vec::new());
+    }
+
+    let sessions = session_store.list_user_sessions(external_id).await?;
+    for session in &sessions {
+        session_store.delete_session(session.id).await?;
+    }
+    Ok(sessions)
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Session Validation
 // ─────────────────────────────────────────────────────────────────────────────
@@ -1013,6 +1044,8 @@ mod tests {
             session_index: None,
             device: None,
             last_activity: None,
+            claims: HashMap::new(),
+            passkey_verified_at: None,
         };
 
         let id = session.id;
@@ -1072,6 +1105,8 @@ mod tests {
             session_index: None,
             device: None,
             last_activity: None,
+            claims: HashMap::new(),
+            passkey_verified_at: None,
         };
 
         assert!(session.is_expired());
@@ -1113,6 +1148,8 @@ mod tests {
             session_index: None,
             device: None,
             last_activity,
+            claims: HashMap::new(),
+            passkey_verified_at: None,
         }
     }
 
@@ -1183,7 +1220,7 @@ mod tests {
 
         // Should return 0 evicted (no-op)
         let evicted = enforce_session_limit(&store, "user1", 3).await.unwrap();
-        assert_eq!(evicted, 0);
+        assert!(evicted.is_empty());
     }
 
     #[tokio::test]
@@ -1192,9 +1229,65 @@ mod tests {
 
         // max_sessions = 0 means unlimited
         let evicted = enforce_session_limit(&store, "user1", 0).await.unwrap();
-        assert_eq!(evicted, 0);
+        assert!(evicted.is_empty());
+    }
+
+    fn enhanced_store() -> CacheSessionStore {
+        let cache = crate::cache::MemoryCache::new(&crate::config::MemoryCacheConfig::default());
+        CacheSessionStore::with_enhanced(Arc::new(cache), true)
+    }
+
+    #[tokio::test]
+    async fn test_enforce_session_limit_evicts_oldest() {
+        let store = enhanced_store();
+        let mut ids = Vec::new();
+        for age_mins in [30, 20, 10] {
+            let mut session = create_test_session("user1", None);
+            session.created_at = Utc::now() - chrono::Duration::minutes(age_mins);
+            ids.push(session.id);
+            store.create_session(session).await.unwrap();
+        }
+
+        let evicted = enforce_session_limit(&store, "user1", 2).await.unwrap();
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].id, ids[0]);
+        assert!(store.get_session(ids[0]).await.unwrap().is_none());
+        assert_eq!(store.count_user_sessions("user1").await.unwrap(), 2);
+
+        // Within the limit now
+        let evicted = enforce_session_limit(&store, "user1", 2).await.unwrap();
+        assert!(evicted.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_revoke_user_sessions_revokes_every_session() {
+        let store = enhanced_store();
+
+        // Sessions from any org's SSO, and ones without an org, all go
+        let mut a = create_test_session("user1", None);
+        a.sso_org_id = Some(Uuid::new_v4());
+        let mut b = create_test_session("user1", None);
+        b.sso_org_id = Some(Uuid::new_v4());
+        let untagged = create_test_session("user1", None);
+        let other_user = create_test_session("user2", None);
+        let other_user_id = other_user.id;
+        for session in [a, b, untagged, other_user] {
+            store.create_session(session).await.unwrap();
+        }
+
+        let revoked = revoke_user_sessions(&store, "user1").await.unwrap();
+        assert_eq!(revoked.len(), 3);
+        assert!(store.list_user_sessions("user1").await.unwrap().is_empty());
+
+        let remaining = store.list_user_sessions("user2").await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, other_user_id);
     }
 
-    // Note: Full eviction tests require CacheSessionStore with enhanced = true,
-    // which needs a Cache implementation. These would be integration tests.
+    #[tokio::test]
+    async fn test_revoke_user_sessions_no_op_when_disabled() {
+        let store = MemorySessionStore::new();
+        let revoked = revoke_user_sessions(&store, "user1").await.unwrap();
+        assert!(revoked.is_empty());
+    }
 }
@@ -0,0 +1,222 @@
+//! Time-based one-time passwords (RFC 6238) for admin step-up authentication.
+//!
+//! Codes are the authenticator-app default: HMAC-SHA1, 30-second steps,
+//! 6 digits. Verification accepts one step of clock skew either side and
+//! returns the matched step so callers can reject replays of the same code.
+
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha1::Sha1;
+use subtle::ConstantTimeEq;
+
+type HmacSha1 = Hmac<Sha1>;
+
+/// Length of a time step in seconds.
+pub const STEP_SECS: i64 = 30;
+
+/// Number of digits in a code.
+pub const DIGITS: u32 = 6;
+
+/// Steps of clock drift tolerated either side of the current step.
+const SKEW_STEPS: i64 = 1;
+
+/// Secret length in bytes (160 bits, as recommended by RFC 4226).
+const SECRET_LEN: usize = 20;
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Generate a new random shared secret, base32-encoded without padding.
+pub fn generate_secret() -> String {
+    let mut bytes = [0u8; SECRET_LEN];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    base32_encode(&bytes)
+}
+
+/// Build the `otpauth://` URI that authenticator apps import from a QR code.
+pub fn otpauth_uri(issuer: &str, account: &str, secret: &str) -> String {
+    let mut url = url::Url::parse("otpauth://totp").expect("static URL is valid");
+    url.path_segments_mut()
+        .expect("otpauth URL has a host")
+        .push(&format!("{issuer}:{account}"));
+    url.query_pairs_mut()
+        .append_pair("secret", secret)
+        .append_pair("issuer", issuer)
+        .append_pair("algorithm", "SHA1")
+        .append_pair("digits", &DIGITS.to_string())
+        .append_pair("period", &STEP_SECS.to_string());
+    url.into()
+}
+
+/// The time step containing `unix_secs`.
+pub fn step_at(unix_secs: i64) -> i64 {
+    unix_secs.div_euclid(STEP_SECS)
+}
+
+/// Check `code` against a base32 `secret` at `unix_secs`.
+///
+/// Returns the matched time step, or `None` if the code is malformed, wrong,
+/// or belongs to a step at or before `last_used_step`.
+pub fn verify(
+    secret: &str,
+    code: &str,
+    unix_secs: i64,
+    last_used_step: Option<i64>,
+) -> Option<i64> {
+    let code = code.trim();
+    if code.len() != DIGITS as usize || !code.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let key = base32_decode(secret)?;
+    let current = step_at(unix_secs);
+
+    let mut matched = None;
+    for step in (current - SKEW_STEPS)..=(current + SKEW_STEPS) {
+        if step < 0 || last_used_step.is_some_and(|last| step <= last) {
+            continue;
+        }
+        let expected = format!(
+            "{:0width$}",
+            hotp(&key, step as u64),
+            width = DIGITS as usize
+        );
+        // Compare every candidate so timing doesn't reveal which step matched
+        if bool::from(expected.as_bytes().ct_eq(code.as_bytes())) {
+            matched = Some(step);
+        }
+    }
+    matched
+}
+
+/// HOTP value (RFC 4226) for `counter`, truncated to [`DIGITS`] digits.
+fn hotp(key: &[u8], counter: u64) -> u32 {
+    let mut mac = HmacSha1::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(&counter.to_be_bytes());
+    let digest = mac.finalize().into_bytes();
+
+    let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+    let binary = u32::from_be_bytes([
+        digest[offset] & 0x7f,
+        digest[offset + 1],
+        digest[offset + 2],
+        digest[offset + 3],
+    ]);
+    binary % 10u32.pow(DIGITS)
+}
+
+/// RFC 4648 base32 without padding.
+pub fn base32_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(5) * 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+    out
+}
+
+/// Decode RFC 4648 base32, ignoring case, whitespace and padding.
+///
+/// Returns `None` on any character outside the alphabet.
+pub fn base32_decode(input: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(input.len() * 5 / 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for c in input.chars() {
+        if c.is_whitespace() || c == '=' {
+            continue;
+        }
+        let value = BASE32_ALPHABET
+            .iter()
+            .position(|&a| a == c.to_ascii_uppercase() as u8)? as u32;
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 6238 Appendix B uses the ASCII secret "12345678901234567890"
+    const RFC_SECRET: &[u8] = b"12345678901234567890";
+
+    fn rfc_secret() -> String {
+        base32_encode(RFC_SECRET)
+    }
+
+    #[test]
+    fn test_base32_round_trip() {
+        assert_eq!(base32_encode(b"foobar"), "MZXW6YTBOI");
+        assert_eq!(base32_decode("MZXW6YTBOI").unwrap(), b"foobar");
+        assert_eq!(base32_decode("mzxw 6ytb oi======").unwrap(), b"foobar");
+        assert!(base32_decode("MZXW1").is_none());
+
+        let secret = generate_secret();
+        assert_eq!(secret.len(), 32);
+        assert_eq!(base32_decode(&secret).unwrap().len(), SECRET_LEN);
+    }
+
+    #[test]
+    fn test_rfc6238_vectors() {
+        // SHA1 rows of the RFC table, truncated to 6 digits
+        let cases = [
+            (59, "287082"),
+            (1111111109, "081804"),
+            (1111111111, "050471"),
+            (1234567890, "005924"),
+            (2000000000, "279037"),
+            (20000000000, "353130"),
+        ];
+        let secret = rfc_secret();
+        for (time, code) in cases {
+            assert_eq!(
+                verify(&secret, code, time, None),
+                Some(step_at(time)),
+                "time {time}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_verify_tolerates_one_step_of_skew() {
+        let secret = rfc_secret();
+        // Code for t=59 (step 1) is accepted during steps 0..=2 only
+        assert_eq!(verify(&secret, "287082", 59 + STEP_SECS, None), Some(1));
+        assert_eq!(verify(&secret, "287082", 59 - STEP_SECS, None), Some(1));
+        assert_eq!(verify(&secret, "287082", 59 + 2 * STEP_SECS, None), None);
+    }
+
+    #[test]
+    fn test_verify_rejects_replayed_and_malformed_codes() {
+        let secret = rfc_secret();
+        assert_eq!(verify(&secret, "287082", 59, Some(1)), None);
+        assert_eq!(verify(&secret, "287082", 59, Some(0)), Some(1));
+        assert_eq!(verify(&secret, "287083", 59, None), None);
+        assert_eq!(verify(&secret, "28708", 59, None), None);
+        assert_eq!(verify(&secret, "28708a", 59, None), None);
+        assert_eq!(verify("not base32!", "287082", 59, None), None);
+    }
+
+    #[test]
+    fn test_otpauth_uri() {
+        let uri = otpauth_uri("Hadrian", "admin@example.com", "JBSWY3DPEHPK3PXP");
+        assert!(uri.starts_with("otpauth://totp/Hadrian:admin@example.com?"));
+        assert!(uri.contains("secret=JBSWY3DPEHPK3PXP"));
+        assert!(uri.contains("issuer=Hadrian"));
+        assert!(uri.contains("digits=6"));
+        assert!(uri.contains("period=30"));
+    }
+}
@@ -0,0 +1,102 @@
+//! WebAuthn ceremonies for admin passkeys.
+//!
+//! Wraps `webauthn-rs` with the `[auth.admin.webauthn]` relying party and
+//! keeps each ceremony's server-side state in the cache between its start
+//! and finish requests. Credentials are stored as serialized [`Passkey`]s.
+
+use std::time::Duration;
+
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+pub use webauthn_rs::prelude::{
+    AuthenticationResult, CreationChallengeResponse, CredentialID, DiscoverableAuthentication,
+    DiscoverableKey, Passkey, PasskeyAuthentication, PasskeyRegistration, PublicKeyCredential,
+    RegisterPublicKeyCredential, RequestChallengeResponse, Webauthn, WebauthnError,
+};
+use webauthn_rs::prelude::{Url, WebauthnBuilder};
+
+use super::AuthError;
+use crate::{
+    cache::{Cache, CacheExt, CacheKeys},
+    config::WebAuthnConfig,
+};
+
+/// Server-side state of a started ceremony.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Ceremony {
+    /// Adding a passkey for `user_id`, started from session `session_id`
+    Registration {
+        user_id: Uuid,
+        session_id: Uuid,
+        name: Option<String>,
+        state: PasskeyRegistration,
+    },
+    /// Verifying one of `user_id`'s passkeys for an existing session
+    SecondFactor {
+        user_id: Uuid,
+        session_id: Uuid,
+        state: PasskeyAuthentication,
+    },
+    /// Signing in with a discoverable passkey; the user is identified by
+    /// the credential the browser returns
+    Login { state: DiscoverableAuthentication },
+}
+
+/// Build the relying party from configuration.
+pub fn relying_party(config: &WebAuthnConfig) -> Result<Webauthn, WebauthnError> {
+    let origin = Url::parse(&config.rp_origin).map_err(|_| WebauthnError::Configuration)?;
+    WebauthnBuilder::new(&config.rp_id, &origin)?
+        .rp_name(&config.rp_name)
+        .build()
+}
+
+/// Encode a credential ID the way it is stored in `admin_passkeys`.
+pub fn encode_credential_id(id: &[u8]) -> String {
+    URL_SAFE_NO_PAD.encode(id)
+}
+
+pub fn serialize_passkey(passkey: &Passkey) -> Result<String, AuthError> {
+    serde_json::to_string(passkey)
+        .map_err(|e| AuthError::Internal(format!("Failed to serialize passkey: {e}")))
+}
+
+pub fn deserialize_passkey(credential: &str) -> Result<Passkey, AuthError> {
+    serde_json::from_str(credential)
+        .map_err(|e| AuthError::Internal(format!("Stored passkey is unreadable: {e}")))
+}
+
+/// Store a ceremony and return the challenge ID the client echoes back.
+pub async fn store_ceremony(
+    cache: &dyn Cache,
+    ceremony: &Ceremony,
+    ttl: Duration,
+) -> Result<String, AuthError> {
+    let mut bytes = [0u8; 24];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    let challenge_id = URL_SAFE_NO_PAD.encode(bytes);
+
+    cache
+        .set_json(&CacheKeys::webauthn_ceremony(&challenge_id), ceremony, ttl)
+        .await
+        .map_err(|e| AuthError::Internal(format!("Failed to store WebAuthn challenge: {e}")))?;
+    Ok(challenge_id)
+}
+
+/// Remove and return a ceremony. Each challenge can be finished once.
+pub async fn take_ceremony(
+    cache: &dyn Cache,
+    challenge_id: &str,
+) -> Result<Option<Ceremony>, AuthError> {
+    let key = CacheKeys::webauthn_ceremony(challenge_id);
+    let ceremony = cache
+        .get_json::<Ceremony>(&key)
+        .await
+        .map_err(|e| AuthError::Internal(format!("Failed to load WebAuthn challenge: {e}")))?;
+    if ceremony.is_some() {
+        let _ = cache.delete(&key).await;
+    }
+    Ok(ceremony)
+}
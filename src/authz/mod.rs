@@ -15,6 +15,7 @@
 mod engine;
 mod error;
 mod registry;
+mod request_policies;
 
 pub use engine::{
     AuthzEngine, AuthzResult, PolicyContext, RequestContext, Subject, SystemPolicySimulationResult,
@@ -24,6 +25,11 @@ pub use error::AuthzError;
 #[cfg(feature = "cel")]
 pub use registry::CompiledOrgPolicy;
 pub use registry::{PolicyRegistry, PolicyRegistryError};
+pub use request_policies::{
+    CompiledRequestPolicy, RequestPolicyDecision, RequestPolicyEvaluation, RequestPolicyInput,
+    RequestPolicyRegistry, RequestPolicySubject, RequestPolicyTraceEntry, compile_request_policies,
+    evaluate_request_policies,
+};
 
 /// Match a pattern against a value.
 ///
@@ -0,0 +1,763 @@
+//! Per-organization request policies for the data plane.
+//!
+//! Where [`PolicyRegistry`](super::PolicyRegistry) answers "may this subject
+//! perform this action", request policies look at the request itself. Each
+//! org's enabled policies are compiled once and cached; every `/v1/*` request
+//! made with the org's credentials is evaluated against them before it
+//! reaches the handler.
+//!
+//! # CEL Variables
+//!
+//! - `request`: `endpoint`, `model`, `estimated_tokens`, `stream`, and when
+//!   the caller sent them `max_tokens`, `temperature` and `metadata`
+//! - `subject`: `org_id`, and when known `user_id`, `api_key_id`,
+//!   `team_id`, `project_id`, `service_account_id`
+//! - `time`: `hour`, `day_of_week` (1 = Monday) and `timestamp`, in UTC
+//!
+//! Optional fields are omitted rather than set to null, so conditions can
+//! guard them with `has(request.max_tokens)`.
+//!
+//! # Evaluation Order
+//!
+//! Policies run by priority (highest first):
+//!
+//! 1. `deny` whose condition matches → reject the request
+//! 2. `allow` whose condition matches → stop evaluating, let it through
+//! 3. `modify` whose condition matches → overwrite top-level request fields
+//!    and continue, so later policies see the modified request
+//! 4. No decisive match → let the request through
+//!
+//! A condition that fails to evaluate denies the request unless
+//! `fail_on_evaluation_error` is off, in which case the policy is skipped.
+//!
+//! # Multi-Node Consistency
+//!
+//! Same scheme as the RBAC registry: policy changes bump a per-org version
+//! in the shared cache, and each node re-checks that version at most once per
+//! `cache_ttl_ms`.
+
+#[cfg(feature = "cel")]
+use std::panic;
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "cel")]
+use cel_interpreter::{Context, Program, Value, to_value};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use super::TimeContext;
+use crate::{
+    cache::{Cache, CacheKeys},
+    config::RequestPoliciesConfig,
+    db::{DbResult, repos::OrgRequestPolicyRepo},
+    models::{OrgRequestPolicy, RequestPolicyAction},
+};
+
+/// Request fields that hold prompt text, used for `estimated_tokens`.
+const PROMPT_FIELDS: &[&str] = &["messages", "input", "prompt", "instructions", "system"];
+
+/// Request attributes exposed to conditions as `request`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct RequestPolicyInput {
+    /// `chat_completions`, `responses`, `completions` or `embeddings`
+    pub endpoint: String,
+    /// Model as sent by the caller (empty if missing)
+    pub model: String,
+    /// Rough prompt size (1 token ≈ 4 characters of message text)
+    pub estimated_tokens: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u64>,
+    #[serde(default)]
+    pub stream: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<BTreeMap<String, serde_json::Value>>,
+}
+
+impl RequestPolicyInput {
+    /// Extract policy attributes from a request body.
+    pub fn from_body(endpoint: &str, body: &serde_json::Value) -> Self {
+        let max_tokens = ["max_tokens", "max_completion_tokens", "max_output_tokens"]
+            .iter()
+            .find_map(|field| body.get(field).and_then(|v| v.as_u64()));
+        let metadata = body.get("metadata").and_then(|m| m.as_object()).map(|m| {
+            m.iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect::<BTreeMap<_, _>>()
+        });
+        let chars: usize = PROMPT_FIELDS
+            .iter()
+            .filter_map(|field| body.get(field))
+            .map(text_chars)
+            .sum();
+
+        Self {
+            endpoint: endpoint.to_string(),
+            model: body
+                .get("model")
+                .and_then(|m| m.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            estimated_tokens: chars.div_ceil(4) as u64,
+            max_tokens,
+            stream: body
+                .get("stream")
+                .and_then(|s| s.as_bool())
+                .unwrap_or(false),
+            temperature: body.get("temperature").and_then(|t| t.as_f64()),
+            metadata,
+        }
+    }
+}
+
+/// Total length of the string leaves under `value`.
+fn text_chars(value: &serde_json::Value) -> usize {
+    match value {
+        serde_json::Value::String(s) => s.len(),
+        serde_json::Value::Array(items) => items.iter().map(text_chars).sum(),
+        serde_json::Value::Object(map) => map.values().map(text_chars).sum(),
+        _ => 0,
+    }
+}
+
+/// Caller attributes exposed to conditions as `subject`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct RequestPolicySubject {
+    pub org_id: Uuid,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<Uuid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_id: Option<Uuid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub team_id: Option<Uuid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<Uuid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service_account_id: Option<Uuid>,
+}
+
+/// Final decision for a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum RequestPolicyDecision {
+    Allow,
+    Deny,
+}
+
+/// How a single policy fared during evaluation.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct RequestPolicyTraceEntry {
+    pub name: String,
+    pub action: RequestPolicyAction,
+    pub priority: i32,
+    /// Whether the condition matched. `None` if it failed to evaluate or
+    /// evaluation stopped before reaching this policy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matched: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Result of evaluating an org's policies against a request.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct RequestPolicyEvaluation {
+    pub decision: RequestPolicyDecision,
+    /// Policy that allowed or denied the request, if any matched
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub policy: Option<String>,
+    /// Reason for a denial
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// `modify` policies applied to the request, in order
+    pub modified_by: Vec<String>,
+    /// Every policy considered, in evaluation order
+    pub trace: Vec<RequestPolicyTraceEntry>,
+}
+
+/// A policy with its condition compiled.
+pub struct CompiledRequestPolicy {
+    pub policy: OrgRequestPolicy,
+    #[cfg(feature = "cel")]
+    program: Result<Arc<Program>, String>,
+}
+
+impl CompiledRequestPolicy {
+    /// Compile `policy`. A condition that fails to compile is kept and
+    /// reported as an evaluation error, so a broken `deny` policy can't
+    /// silently let requests through.
+    pub fn compile(policy: OrgRequestPolicy, max_expression_length: usize) -> Self {
+        #[cfg(feature = "cel")]
+        {
+            let program = compile_condition(&policy.condition, max_expression_length);
+            if let Err(e) = &program {
+                tracing::warn!(
+                    org_id = %policy.org_id,
+                    policy = %policy.name,
+                    error = %e,
+                    "Failed to compile request policy"
+                );
+            }
+            Self { policy, program }
+        }
+        #[cfg(not(feature = "cel"))]
+        {
+            let _ = max_expression_length;
+            Self { policy }
+        }
+    }
+
+    #[cfg(feature = "cel")]
+    fn matches(&self, ctx: &Context) -> Result<bool, String> {
+        let program = self.program.as_ref().map_err(Clone::clone)?;
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| program.execute(ctx)));
+        match result {
+            Ok(Ok(Value::Bool(b))) => Ok(b),
+            Ok(Ok(_)) => Err("condition must evaluate to a boolean".to_string()),
+            Ok(Err(e)) => Err(format!("execution error: {e}")),
+            Err(_) => Err("CEL expression execution failed (internal error)".to_string()),
+        }
+    }
+}
+
+#[cfg(feature = "cel")]
+fn compile_condition(
+    condition: &str,
+    max_expression_length: usize,
+) -> Result<Arc<Program>, String> {
+    if max_expression_length > 0 && condition.len() > max_expression_length {
+        return Err(format!(
+            "CEL expression length ({} bytes) exceeds maximum ({} bytes)",
+            condition.len(),
+            max_expression_length
+        ));
+    }
+    match panic::catch_unwind(|| Program::compile(condition)) {
+        Ok(Ok(program)) => Ok(Arc::new(program)),
+        Ok(Err(e)) => Err(e.to_string()),
+        Err(_) => Err("CEL expression parsing failed (malformed syntax)".to_string()),
+    }
+}
+
+/// Compile and sort policies for evaluation, dropping disabled ones.
+pub fn compile_request_policies(
+    policies: Vec<OrgRequestPolicy>,
+    max_expression_length: usize,
+) -> Vec<CompiledRequestPolicy> {
+    let mut compiled: Vec<_> = policies
+        .into_iter()
+        .filter(|p| p.enabled)
+        .map(|p| CompiledRequestPolicy::compile(p, max_expression_length))
+        .collect();
+    // Highest priority first; at equal priority deny, then allow, then modify
+    compiled.sort_by(|a, b| {
+        b.policy
+            .priority
+            .cmp(&a.policy.priority)
+            .then_with(|| action_rank(a.policy.action).cmp(&action_rank(b.policy.action)))
+    });
+    compiled
+}
+
+fn action_rank(action: RequestPolicyAction) -> u8 {
+    match action {
+        RequestPolicyAction::Deny => 0,
+        RequestPolicyAction::Allow => 1,
+        RequestPolicyAction::Modify => 2,
+    }
+}
+
+/// Evaluate `policies` (already sorted) against `body`, applying any
+/// matching `modify` policies to it in place.
+pub fn evaluate_request_policies(
+    policies: &[CompiledRequestPolicy],
+    endpoint: &str,
+    subject: &RequestPolicySubject,
+    time: &TimeContext,
+    body: &mut serde_json::Value,
+    fail_on_evaluation_error: bool,
+) -> RequestPolicyEvaluation {
+    let mut evaluation = RequestPolicyEvaluation {
+        decision: RequestPolicyDecision::Allow,
+        policy: None,
+        message: None,
+        modified_by: Vec::new(),
+        trace: Vec::with_capacity(policies.len()),
+    };
+    let mut request = RequestPolicyInput::from_body(endpoint, body);
+    let mut decided = false;
+
+    for compiled in policies {
+        let policy = &compiled.policy;
+        let mut entry = RequestPolicyTraceEntry {
+            name: policy.name.clone(),
+            action: policy.action,
+            priority: policy.priority,
+            matched: None,
+            error: None,
+        };
+        if decided {
+            evaluation.trace.push(entry);
+            continue;
+        }
+
+        match condition_matches(compiled, &request, subject, time) {
+            Ok(matched) => entry.matched = Some(matched),
+            Err(e) => {
+                tracing::warn!(
+                    org_id = %policy.org_id,
+                    policy = %policy.name,
+                    error = %e,
+                    fail_on_evaluation_error,
+                    "Request policy evaluation error"
+                );
+                if fail_on_evaluation_error {
+                    evaluation.decision = RequestPolicyDecision::Deny;
+                    evaluation.policy = Some(policy.name.clone());
+                    evaluation.message =
+                        Some(format!("Policy '{}' failed to evaluate", policy.name));
+                    decided = true;
+                }
+                entry.error = Some(e);
+            }
+        }
+
+        if entry.matched == Some(true) {
+            match policy.action {
+                RequestPolicyAction::Deny => {
+                    evaluation.decision = RequestPolicyDecision::Deny;
+                    evaluation.policy = Some(policy.name.clone());
+                    evaluation.message =
+                        Some(policy.message.clone().unwrap_or_else(|| {
+                            format!("Request denied by policy '{}'", policy.name)
+                        }));
+                    decided = true;
+                }
+                RequestPolicyAction::Allow => {
+                    evaluation.policy = Some(policy.name.clone());
+                    decided = true;
+                }
+                RequestPolicyAction::Modify => {
+                    if let Some(modifications) = &policy.modifications {
+                        apply_modifications(body, modifications);
+                        request = RequestPolicyInput::from_body(endpoint, body);
+                    }
+                    evaluation.modified_by.push(policy.name.clone());
+                }
+            }
+        }
+        evaluation.trace.push(entry);
+    }
+
+    evaluation
+}
+
+/// Overwrite top-level fields of `body`. A `null` modification removes the
+/// field.
+pub fn apply_modifications(body: &mut serde_json::Value, modifications: &serde_json::Value) {
+    let (Some(body), Some(modifications)) = (body.as_object_mut(), modifications.as_object())
+    else {
+        return;
+    };
+    for (key, value) in modifications {
+        if value.is_null() {
+            body.remove(key);
+        } else {
+            body.insert(key.clone(), value.clone());
+        }
+    }
+}
+
+#[cfg(feature = "cel")]
+fn condition_matches(
+    compiled: &CompiledRequestPolicy,
+    request: &RequestPolicyInput,
+    subject: &RequestPolicySubject,
+    time: &TimeContext,
+) -> Result<bool, String> {
+    let mut ctx = Context::default();
+    ctx.add_variable(
+        "request",
+        to_value(request).map_err(|e| format!("failed to serialize request: {e}"))?,
+    );
+    ctx.add_variable(
+        "subject",
+        to_value(subject).map_err(|e| format!("failed to serialize subject: {e}"))?,
+    );
+    ctx.add_variable(
+        "time",
+        to_value(time).map_err(|e| format!("failed to serialize time: {e}"))?,
+    );
+    compiled.matches(&ctx)
+}
+
+#[cfg(not(feature = "cel"))]
+fn condition_matches(
+    _compiled: &CompiledRequestPolicy,
+    _request: &RequestPolicyInput,
+    _subject: &RequestPolicySubject,
+    _time: &TimeContext,
+) -> Result<bool, String> {
+    Err("request policies require the 'cel' feature to be enabled".to_string())
+}
+
+/// Cached, compiled policies for one organization.
+struct CachedOrgRequestPolicies {
+    policies: Arc<Vec<CompiledRequestPolicy>>,
+    /// Version from the shared cache when these were loaded
+    version: u64,
+    last_version_check: Instant,
+}
+
+/// Compiled request policies per organization, loaded on first use.
+pub struct RequestPolicyRegistry {
+    repo: Arc<dyn OrgRequestPolicyRepo>,
+    /// Shared cache for cross-node version checks
+    cache: Option<Arc<dyn Cache>>,
+    version_check_ttl: Duration,
+    max_expression_length: usize,
+    fail_on_evaluation_error: bool,
+    orgs: RwLock<HashMap<Uuid, CachedOrgRequestPolicies>>,
+}
+
+impl RequestPolicyRegistry {
+    pub fn new(
+        repo: Arc<dyn OrgRequestPolicyRepo>,
+        cache: Option<Arc<dyn Cache>>,
+        config: &RequestPoliciesConfig,
+        max_expression_length: usize,
+    ) -> Self {
+        Self {
+            repo,
+            cache,
+            version_check_ttl: Duration::from_millis(config.cache_ttl_ms),
+            max_expression_length,
+            fail_on_evaluation_error: config.fail_on_evaluation_error,
+            orgs: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn max_expression_length(&self) -> usize {
+        self.max_expression_length
+    }
+
+    pub fn fail_on_evaluation_error(&self) -> bool {
+        self.fail_on_evaluation_error
+    }
+
+    /// Evaluate the org's policies against `body`, modifying it in place.
+    ///
+    /// Returns an error only if the org's policies aren't cached and can't
+    /// be loaded.
+    pub async fn evaluate(
+        &self,
+        subject: &RequestPolicySubject,
+        endpoint: &str,
+        body: &mut serde_json::Value,
+    ) -> DbResult<RequestPolicyEvaluation> {
+        let policies = self.policies_for(subject.org_id).await?;
+        Ok(evaluate_request_policies(
+            &policies,
+            endpoint,
+            subject,
+            &TimeContext::now(),
+            body,
+            self.fail_on_evaluation_error,
+        ))
+    }
+
+    /// Drop the org's compiled policies and signal other nodes to do the
+    /// same. Call after any policy change.
+    pub async fn invalidate(&self, org_id: Uuid) {
+        if let Some(cache) = &self.cache {
+            let key = CacheKeys::request_policy_version(org_id);
+            // Policies are reloaded at least this often anyway; the TTL only
+            // bounds how long an idle org's counter lingers.
+            if let Err(e) = cache.incr(&key, Duration::from_secs(86400 * 30)).await {
+                tracing::warn!(org_id = %org_id, error = %e, "Failed to bump request policy version");
+            }
+        }
+        self.orgs.write().await.remove(&org_id);
+    }
+
+    /// The org's compiled policies, reloading them if another node has
+    /// changed them since they were cached.
+    async fn policies_for(&self, org_id: Uuid) -> DbResult<Arc<Vec<CompiledRequestPolicy>>> {
+        let cached_version = {
+            let orgs = self.orgs.read().await;
+            match orgs.get(&org_id) {
+                Some(cached) if self.cache.is_none() => return Ok(cached.policies.clone()),
+                Some(cached) if cached.last_version_check.elapsed() < self.version_check_ttl => {
+                    return Ok(cached.policies.clone());
+                }
+                Some(cached) => Some(cached.version),
+                None => None,
+            }
+        };
+
+        let version = self.shared_version(org_id).await;
+        if let (Some(cached_version), Some(version)) = (cached_version, version)
+            && version <= cached_version
+        {
+            let mut orgs = self.orgs.write().await;
+            if let Some(cached) = orgs.get_mut(&org_id) {
+                cached.last_version_check = Instant::now();
+                return Ok(cached.policies.clone());
+            }
+        }
+
+        let policies = match self.repo.list_enabled_by_org(org_id).await {
+            Ok(policies) => policies,
+            Err(e) => {
+                // Keep serving what we have rather than failing every request
+                let orgs = self.orgs.read().await;
+                if let Some(cached) = orgs.get(&org_id) {
+                    tracing::warn!(org_id = %org_id, error = %e, "Failed to reload request policies, using cached copy");
+                    return Ok(cached.policies.clone());
+                }
+                return Err(e);
+            }
+        };
+        let compiled = Arc::new(compile_request_policies(
+            policies,
+            self.max_expression_length,
+        ));
+
+        self.orgs.write().await.insert(
+            org_id,
+            CachedOrgRequestPolicies {
+                policies: compiled.clone(),
+                version: version.unwrap_or(0),
+                last_version_check: Instant::now(),
+            },
+        );
+        tracing::debug!(org_id = %org_id, policy_count = compiled.len(), "Loaded request policies");
+
+        Ok(compiled)
+    }
+
+    /// Current version in the shared cache, `None` if there's no shared
+    /// cache or it couldn't be read.
+    async fn shared_version(&self, org_id: Uuid) -> Option<u64> {
+        let cache = self.cache.as_ref()?;
+        let key = CacheKeys::request_policy_version(org_id);
+        match cache.get_bytes(&key).await {
+            Ok(Some(bytes)) => Some(String::from_utf8_lossy(&bytes).parse().unwrap_or(0)),
+            Ok(None) => Some(0),
+            Err(e) => {
+                tracing::warn!(org_id = %org_id, error = %e, "Failed to read request policy version");
+                None
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "cel"))]
+mod tests {
+    use chrono::Utc;
+    use serde_json::json;
+
+    use super::*;
+
+    fn policy(
+        name: &str,
+        condition: &str,
+        action: RequestPolicyAction,
+        priority: i32,
+    ) -> OrgRequestPolicy {
+        OrgRequestPolicy {
+            id: Uuid::new_v4(),
+            org_id: Uuid::nil(),
+            name: name.to_string(),
+            description: None,
+            condition: condition.to_string(),
+            action,
+            modifications: None,
+            message: None,
+            priority,
+            enabled: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn evaluate(
+        policies: Vec<OrgRequestPolicy>,
+        body: &mut serde_json::Value,
+        fail_on_error: bool,
+    ) -> RequestPolicyEvaluation {
+        let compiled = compile_request_policies(policies, 4096);
+        evaluate_request_policies(
+            &compiled,
+            "chat_completions",
+            &RequestPolicySubject::default(),
+            &TimeContext::with_values(14, 3, 0),
+            body,
+            fail_on_error,
+        )
+    }
+
+    #[test]
+    fn test_input_from_body() {
+        let body = json!({
+            "model": "gpt-4o",
+            "messages": [{"role": "user", "content": "abcdefgh"}],
+            "max_completion_tokens": 512,
+            "stream": true,
+            "metadata": {"team": "search"}
+        });
+        let input = RequestPolicyInput::from_body("chat_completions", &body);
+        assert_eq!(input.model, "gpt-4o");
+        // "user" + "abcdefgh" = 12 chars
+        assert_eq!(input.estimated_tokens, 3);
+        assert_eq!(input.max_tokens, Some(512));
+        assert!(input.stream);
+        assert_eq!(input.metadata.unwrap()["team"], "search");
+    }
+
+    #[test]
+    fn test_no_policies_allows() {
+        let mut body = json!({"model": "gpt-4o"});
+        let result = evaluate(vec![], &mut body, true);
+        assert_eq!(result.decision, RequestPolicyDecision::Allow);
+        assert!(result.policy.is_none());
+    }
+
+    #[test]
+    fn test_deny_with_message() {
+        let mut deny = policy(
+            "no-gpt4",
+            "request.model.startsWith('gpt-4')",
+            RequestPolicyAction::Deny,
+            0,
+        );
+        deny.message = Some("GPT-4 is not approved".to_string());
+
+        let mut body = json!({"model": "gpt-4o"});
+        let result = evaluate(vec![deny.clone()], &mut body, true);
+        assert_eq!(result.decision, RequestPolicyDecision::Deny);
+        assert_eq!(result.message.as_deref(), Some("GPT-4 is not approved"));
+
+        let mut body = json!({"model": "claude-sonnet"});
+        let result = evaluate(vec![deny], &mut body, true);
+        assert_eq!(result.decision, RequestPolicyDecision::Allow);
+    }
+
+    #[test]
+    fn test_allow_short_circuits_lower_priority_deny() {
+        let mut body = json!({"model": "gpt-4o", "metadata": {"tier": "gold"}});
+        let result = evaluate(
+            vec![
+                policy("deny-all", "true", RequestPolicyAction::Deny, 0),
+                policy(
+                    "gold",
+                    "has(request.metadata) && request.metadata.tier == 'gold'",
+                    RequestPolicyAction::Allow,
+                    10,
+                ),
+            ],
+            &mut body,
+            true,
+        );
+        assert_eq!(result.decision, RequestPolicyDecision::Allow);
+        assert_eq!(result.policy.as_deref(), Some("gold"));
+        assert_eq!(result.trace.len(), 2);
+        assert_eq!(result.trace[1].name, "deny-all");
+        assert!(result.trace[1].matched.is_none());
+    }
+
+    #[test]
+    fn test_modify_applies_and_later_policies_see_it() {
+        let mut cap = policy(
+            "cap",
+            "!has(request.max_tokens) || request.max_tokens > 1000",
+            RequestPolicyAction::Modify,
+            10,
+        );
+        cap.modifications = Some(json!({"max_tokens": 1000, "user": null}));
+        let guard = policy(
+            "guard",
+            "request.max_tokens > 1000",
+            RequestPolicyAction::Deny,
+            0,
+        );
+
+        let mut body = json!({"model": "gpt-4o", "max_tokens": 8000, "user": "u1"});
+        let result = evaluate(vec![cap, guard], &mut body, true);
+        assert_eq!(result.decision, RequestPolicyDecision::Allow);
+        assert_eq!(result.modified_by, vec!["cap"]);
+        assert_eq!(body["max_tokens"], 1000);
+        assert!(body.get("user").is_none());
+    }
+
+    #[test]
+    fn test_time_of_day() {
+        let mut body = json!({"model": "gpt-4o"});
+        let result = evaluate(
+            vec![policy(
+                "business-hours",
+                "time.hour < 9 || time.hour >= 17",
+                RequestPolicyAction::Deny,
+                0,
+            )],
+            &mut body,
+            true,
+        );
+        // Evaluated at 14:00
+        assert_eq!(result.decision, RequestPolicyDecision::Allow);
+    }
+
+    #[test]
+    fn test_evaluation_error_fails_closed_unless_disabled() {
+        let broken = policy(
+            "broken",
+            "request.max_tokens > 10",
+            RequestPolicyAction::Deny,
+            0,
+        );
+
+        let mut body = json!({"model": "gpt-4o"});
+        let result = evaluate(vec![broken.clone()], &mut body, true);
+        assert_eq!(result.decision, RequestPolicyDecision::Deny);
+        assert!(result.trace[0].error.is_some());
+
+        let result = evaluate(vec![broken], &mut body, false);
+        assert_eq!(result.decision, RequestPolicyDecision::Allow);
+    }
+
+    #[test]
+    fn test_uncompilable_condition_is_an_evaluation_error() {
+        let mut body = json!({"model": "gpt-4o"});
+        let result = evaluate(
+            vec![policy(
+                "bad-syntax",
+                "request.model ==",
+                RequestPolicyAction::Deny,
+                0,
+            )],
+            &mut body,
+            true,
+        );
+        assert_eq!(result.decision, RequestPolicyDecision::Deny);
+    }
+
+    #[test]
+    fn test_disabled_policies_are_skipped() {
+        let mut deny = policy("deny-all", "true", RequestPolicyAction::Deny, 0);
+        deny.enabled = false;
+        let mut body = json!({"model": "gpt-4o"});
+        let result = evaluate(vec![deny], &mut body, true);
+        assert_eq!(result.decision, RequestPolicyDecision::Allow);
+        assert!(result.trace.is_empty());
+    }
+}
@@ -0,0 +1,276 @@
+//! Response caching for the embeddings API.
+//!
+//! Embeddings are deterministic for a given model and input, so RAG pipelines
+//! that re-embed the same chunks or queries can be served from cache instead
+//! of the provider.
+//!
+//! Entries are keyed by tenant, model, normalized input, dimensions, encoding
+//! format and input type (see [`CacheKeys::embeddings_cache`]). A single
+//! string and a one-element batch share an entry.
+//!
+//! # Configuration
+//!
+//! ```toml
+//! [features.response_caching.embeddings]
+//! enabled = true
+//! ttl_secs = 86400             # Cache TTL (default: 24 hours)
+//! max_size_bytes = 8388608     # Max response size to cache (default: 8MB)
+//! ```
+
+use std::{sync::Arc, time::Duration};
+
+use super::{
+    keys::{CacheKeys, CacheTenantScope},
+    response_cache::{CacheLookupResult, CachedResponse},
+    traits::{Cache, CacheExt},
+};
+use crate::{
+    api_types::CreateEmbeddingPayload, config::EmbeddingsCachingConfig, observability::metrics,
+};
+
+/// Embeddings response cache service.
+pub struct EmbeddingsCache {
+    cache: Arc<dyn Cache>,
+    config: EmbeddingsCachingConfig,
+}
+
+impl EmbeddingsCache {
+    /// Create a new embeddings cache service.
+    pub fn new(cache: Arc<dyn Cache>, config: EmbeddingsCachingConfig) -> Self {
+        Self { cache, config }
+    }
+
+    /// Look up a cached embeddings response.
+    ///
+    /// `force_refresh` skips the lookup but still lets the fresh response be
+    /// stored.
+    pub async fn lookup(
+        &self,
+        payload: &CreateEmbeddingPayload,
+        model: &str,
+        tenant: &CacheTenantScope,
+        force_refresh: bool,
+    ) -> CacheLookupResult {
+        if !self.config.enabled {
+            return CacheLookupResult::Bypass;
+        }
+        if force_refresh {
+            tracing::debug!("Embeddings cache force refresh requested");
+            return CacheLookupResult::Miss;
+        }
+
+        let cache_key = CacheKeys::embeddings_cache(payload, model, tenant);
+
+        match self.cache.get_json::<CachedResponse>(&cache_key).await {
+            Ok(Some(cached)) => {
+                metrics::record_embeddings_cache_lookup("hit");
+                tracing::debug!(
+                    cache_key = %cache_key,
+                    provider = %cached.provider,
+                    model = %cached.model,
+                    "Embeddings cache hit"
+                );
+                CacheLookupResult::Hit(cached)
+            }
+            Ok(None) => {
+                metrics::record_embeddings_cache_lookup("miss");
+                tracing::debug!(cache_key = %cache_key, "Embeddings cache miss");
+                CacheLookupResult::Miss
+            }
+            Err(e) => {
+                metrics::record_embeddings_cache_lookup("error");
+                tracing::warn!(
+                    cache_key = %cache_key,
+                    error = %e,
+                    "Embeddings cache lookup error, treating as miss"
+                );
+                CacheLookupResult::Miss
+            }
+        }
+    }
+
+    /// Store an embeddings response. Returns whether it was cached.
+    pub async fn store(
+        &self,
+        payload: &CreateEmbeddingPayload,
+        model: &str,
+        provider: &str,
+        tenant: &CacheTenantScope,
+        body: Vec<u8>,
+        content_type: &str,
+    ) -> bool {
+        if !self.config.enabled {
+            return false;
+        }
+
+        if body.len() > self.config.max_size_bytes {
+            metrics::record_embeddings_cache_store("too_large");
+            tracing::debug!(
+                size = body.len(),
+                max_size = self.config.max_size_bytes,
+                "Embeddings response too large to cache"
+            );
+            return false;
+        }
+
+        let cache_key = CacheKeys::embeddings_cache(payload, model, tenant);
+        let cached = CachedResponse {
+            body,
+            content_type: content_type.to_string(),
+            provider: provider.to_string(),
+            model: model.to_string(),
+            cached_at: chrono::Utc::now().timestamp(),
+        };
+
+        let ttl = Duration::from_secs(self.config.ttl_secs);
+        match self.cache.set_json(&cache_key, &cached, ttl).await {
+            Ok(()) => {
+                metrics::record_embeddings_cache_store("stored");
+                tracing::debug!(
+                    cache_key = %cache_key,
+                    provider = %provider,
+                    model = %model,
+                    size = cached.body.len(),
+                    ttl_secs = self.config.ttl_secs,
+                    "Embeddings response cached"
+                );
+                true
+            }
+            Err(e) => {
+                metrics::record_embeddings_cache_store("error");
+                tracing::warn!(
+                    cache_key = %cache_key,
+                    error = %e,
+                    "Failed to cache embeddings response"
+                );
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api_types::embeddings::EmbeddingInput, cache::MemoryCache, config::MemoryCacheConfig,
+    };
+
+    fn create_test_cache(config: EmbeddingsCachingConfig) -> EmbeddingsCache {
+        EmbeddingsCache::new(
+            Arc::new(MemoryCache::new(&MemoryCacheConfig::default())),
+            config,
+        )
+    }
+
+    fn create_test_payload(input: EmbeddingInput) -> CreateEmbeddingPayload {
+        CreateEmbeddingPayload {
+            input,
+            model: "text-embedding-3-small".to_string(),
+            encoding_format: None,
+            dimensions: None,
+            user: None,
+            provider: None,
+            input_type: None,
+            sovereignty_requirements: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_miss_then_hit_across_input_shapes() {
+        let cache = create_test_cache(EmbeddingsCachingConfig::default());
+        let tenant = CacheTenantScope::unscoped();
+        let single = create_test_payload(EmbeddingInput::Text("hello".to_string()));
+        let batch = create_test_payload(EmbeddingInput::TextArray(vec!["hello".to_string()]));
+
+        assert!(matches!(
+            cache.lookup(&single, "m", &tenant, false).await,
+            CacheLookupResult::Miss
+        ));
+        assert!(
+            cache
+                .store(
+                    &single,
+                    "m",
+                    "openai",
+                    &tenant,
+                    b"{\"data\":[]}".to_vec(),
+                    "application/json"
+                )
+                .await
+        );
+
+        match cache.lookup(&batch, "m", &tenant, false).await {
+            CacheLookupResult::Hit(cached) => {
+                assert_eq!(cached.body, b"{\"data\":[]}");
+                assert_eq!(cached.provider, "openai");
+            }
+            other => panic!("expected hit, got {other:?}"),
+        }
+
+        let mut dims = batch.clone();
+        dims.dimensions = Some(256);
+        assert!(matches!(
+            cache.lookup(&dims, "m", &tenant, false).await,
+            CacheLookupResult::Miss
+        ));
+        assert!(matches!(
+            cache.lookup(&batch, "m", &tenant, true).await,
+            CacheLookupResult::Miss
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_disabled_bypasses_cache() {
+        let cache = create_test_cache(EmbeddingsCachingConfig {
+            enabled: false,
+            ..Default::default()
+        });
+        let tenant = CacheTenantScope::unscoped();
+        let payload = create_test_payload(EmbeddingInput::Text("hello".to_string()));
+
+        assert!(matches!(
+            cache.lookup(&payload, "m", &tenant, true).await,
+            CacheLookupResult::Bypass
+        ));
+        assert!(
+            !cache
+                .store(
+                    &payload,
+                    "m",
+                    "openai",
+                    &tenant,
+                    vec![0; 8],
+                    "application/json"
+                )
+                .await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_response_too_large() {
+        let cache = create_test_cache(EmbeddingsCachingConfig {
+            max_size_bytes: 16,
+            ..Default::default()
+        });
+        let tenant = CacheTenantScope::unscoped();
+        let payload = create_test_payload(EmbeddingInput::Text("hello".to_string()));
+
+        assert!(
+            !cache
+                .store(
+                    &payload,
+                    "m",
+                    "openai",
+                    &tenant,
+                    vec![0; 17],
+                    "application/json"
+                )
+                .await
+        );
+        assert!(matches!(
+            cache.lookup(&payload, "m", &tenant, false).await,
+            CacheLookupResult::Miss
+        ));
+    }
+}
@@ -8,6 +8,7 @@ use crate::{
     api_types::{
         CreateChatCompletionPayload, CreateCompletionPayload, CreateEmbeddingPayload,
         CreateResponsesPayload, Message, MessageContent,
+        embeddings::{EmbeddingInput, EncodingFormat},
     },
     config::CacheKeyComponents,
     models::BudgetPeriod,
@@ -90,6 +91,13 @@ impl CacheKeys {
         format!("gw:ratelimit:tokens:{{{}}}:{}", api_key_id, window)
     }
 
+    /// Temporary rate limit from anomaly detection: gw:ratelimit:anomaly:{api_key_id}
+    ///
+    /// Holds the requests-per-minute cap; expires when the limit lifts.
+    pub fn anomaly_rate_limit(api_key_id: Uuid) -> String {
+        format!("gw:ratelimit:anomaly:{}", api_key_id)
+    }
+
     /// Concurrent requests: gw:concurrent:{api_key_id}
     ///
     /// Uses Redis hash tags `{api_key_id}` to ensure all keys for the same API key
@@ -121,6 +129,73 @@ impl CacheKeys {
         format!("gw:orgaccess:{}:{}", user_id, org_id)
     }
 
+    /// Organization data residency policy: gw:org:{org_id}:residency
+    ///
+    /// Caches the org's `data_residency` policy for data-plane routing. Deleted
+    /// when the policy is updated via the admin API.
+    pub fn org_data_residency(org_id: Uuid) -> String {
+        format!("gw:org:{}:residency", org_id)
+    }
+
+    /// Organization request defaults: gw:org:{org_id}:defaults
+    ///
+    /// Caches the org's default model/temperature/system prompt. Deleted when
+    /// the defaults are updated via the admin API.
+    pub fn org_request_defaults(org_id: Uuid) -> String {
+        format!("gw:org:{}:defaults", org_id)
+    }
+
+    /// Organization network policy: gw:org:{org_id}:network
+    ///
+    /// Caches the CIDR ranges the org's API keys may be used from. Deleted
+    /// when the policy is changed via the admin API.
+    pub fn org_network_policy(org_id: Uuid) -> String {
+        format!("gw:org:{}:network", org_id)
+    }
+
+    /// Organization parameter governance rules: gw:org:{org_id}:governance
+    pub fn org_parameter_governance(org_id: Uuid) -> String {
+        format!("gw:org:{}:governance", org_id)
+    }
+
+    /// Organization model access policy: gw:org:{org_id}:model_access
+    pub fn org_model_access(org_id: Uuid) -> String {
+        format!("gw:org:{}:model_access", org_id)
+    }
+
+    /// Organization model degradation policy: gw:org:{org_id}:model_degradation
+    pub fn org_model_degradation(org_id: Uuid) -> String {
+        format!("gw:org:{}:model_degradation", org_id)
+    }
+
+    /// Organization slug by ID: gw:org:{org_id}:slug
+    ///
+    /// Used to match org-scoped transform rules without a database round
+    /// trip per request.
+    pub fn org_slug(org_id: Uuid) -> String {
+        format!("gw:org:{}:slug", org_id)
+    }
+
+    /// Organization plan and feature overrides: gw:org:{org_id}:entitlements
+    pub fn org_entitlements(org_id: Uuid) -> String {
+        format!("gw:org:{}:entitlements", org_id)
+    }
+
+    /// Project request defaults: gw:project:{project_id}:defaults
+    pub fn project_request_defaults(project_id: Uuid) -> String {
+        format!("gw:project:{}:defaults", project_id)
+    }
+
+    /// Project parameter governance rules: gw:project:{project_id}:governance
+    pub fn project_parameter_governance(project_id: Uuid) -> String {
+        format!("gw:project:{}:governance", project_id)
+    }
+
+    /// Project model access policy: gw:project:{project_id}:model_access
+    pub fn project_model_access(project_id: Uuid) -> String {
+        format!("gw:project:{}:model_access", project_id)
+    }
+
     /// API key last_used_at debounce: gw:apikey:lastused:{id}
     ///
     /// Presence of this key means a `last_used_at` write was already issued
@@ -137,6 +212,14 @@ impl CacheKeys {
         format!("gw:provider:{}:{}:{}", scope, scope_id, name)
     }
 
+    /// Shared circuit breaker open state: gw:circuit:{provider}
+    ///
+    /// Written by the node that opened the circuit, with a TTL matching the
+    /// open timeout, and polled by other nodes in shared mode.
+    pub fn circuit_breaker(provider: &str) -> String {
+        format!("gw:circuit:{}", provider)
+    }
+
     #[cfg(feature = "cel")]
     /// RBAC policy version for multi-node cache invalidation: gw:rbac:org:{org_id}:version
     ///
@@ -147,6 +230,14 @@ impl CacheKeys {
         format!("gw:rbac:org:{}:version", org_id)
     }
 
+    /// Request policy version: gw:request_policy:org:{org_id}:version
+    ///
+    /// Bumped whenever an organization's request policies change so other
+    /// nodes recompile their cached copy.
+    pub fn request_policy_version(org_id: Uuid) -> String {
+        format!("gw:request_policy:org:{}:version", org_id)
+    }
+
     /// Emergency access rate limiting: gw:emergency:ratelimit:{ip}
     ///
     /// Tracks failed emergency access attempts from an IP address.
@@ -179,6 +270,53 @@ impl CacheKeys {
         format!("gw:bootstrap:lockout:{}", ip)
     }
 
+    /// Step-up verification: gw:step_up:verified:{user_id}
+    ///
+    /// Set after an admin verifies a TOTP code; holds the expiry as a Unix
+    /// timestamp. Presence satisfies step-up for protected admin routes.
+    pub fn step_up_verified(user_id: Uuid) -> String {
+        format!("gw:step_up:verified:{}", user_id)
+    }
+
+    /// Step-up failure counter: gw:step_up:failures:{user_id}
+    ///
+    /// Counts failed TOTP codes for a user within the verification window.
+    pub fn step_up_failures(user_id: Uuid) -> String {
+        format!("gw:step_up:failures:{}", user_id)
+    }
+
+    /// Step-up lockout: gw:step_up:lockout:{user_id}
+    ///
+    /// Set when a user exceeds the failed-code threshold. Presence blocks
+    /// further TOTP attempts until it expires.
+    pub fn step_up_lockout(user_id: Uuid) -> String {
+        format!("gw:step_up:lockout:{}", user_id)
+    }
+
+    /// WebAuthn ceremony: gw:webauthn:ceremony:{challenge_id}
+    ///
+    /// Server-side state of a started passkey registration or authentication,
+    /// consumed when the ceremony is finished.
+    pub fn webauthn_ceremony(challenge_id: &str) -> String {
+        format!("gw:webauthn:ceremony:{}", challenge_id)
+    }
+
+    /// Idempotency key: gw:idempotency:{key_hash}
+    ///
+    /// Claim and stored response for a client-supplied `Idempotency-Key`,
+    /// hashed together with the caller and endpoint.
+    pub fn idempotency(key_hash: &str) -> String {
+        format!("gw:idempotency:{}", key_hash)
+    }
+
+    /// Semantic cache counter: gw:semantic:stats:{org_id}:{field}
+    ///
+    /// Per-organization lookup outcome and similarity-bucket counters,
+    /// reported by the admin semantic cache endpoint and reset on purge.
+    pub fn semantic_cache_stat(org_id: &str, field: &str) -> String {
+        format!("gw:semantic:stats:{}:{}", org_id, field)
+    }
+
     /// Response cache key for chat completions.
     ///
     /// Generates a deterministic cache key based on configurable components:
@@ -393,9 +531,11 @@ impl CacheKeys {
     ///
     /// Generates a deterministic cache key based on:
     /// - Model name (always included)
-    /// - Input content (always included, serialized and hashed)
-    /// - Encoding format (if specified)
+    /// - Input content, normalized so a single string or token list hashes
+    ///   the same as a one-element batch
+    /// - Encoding format (an omitted format is treated as `float`)
     /// - Dimensions (if specified)
+    /// - Input type hint (if specified; it changes the vectors for some providers)
     ///
     /// Note: Embeddings are fully deterministic (no temperature/seed),
     /// making them excellent candidates for caching.
@@ -415,14 +555,12 @@ impl CacheKeys {
         hasher.update(model.as_bytes());
         hasher.update(b"\x00");
 
-        // Include encoding_format if present
-        if let Some(ref format) = payload.encoding_format {
-            hasher.update(b"encoding:");
-            if let Ok(json) = serde_json::to_string(format) {
-                hasher.update(json.as_bytes());
-            }
-            hasher.update(b"\x00");
+        hasher.update(b"encoding:");
+        let format = payload.encoding_format.unwrap_or(EncodingFormat::Float);
+        if let Ok(json) = serde_json::to_string(&format) {
+            hasher.update(json.as_bytes());
         }
+        hasher.update(b"\x00");
 
         // Include dimensions if present
         if let Some(dimensions) = payload.dimensions {
@@ -431,9 +569,20 @@ impl CacheKeys {
             hasher.update(b"\x00");
         }
 
-        // Always include input content (hashed via JSON serialization)
+        if let Some(ref input_type) = payload.input_type {
+            hasher.update(b"input_type:");
+            hasher.update(input_type.as_bytes());
+            hasher.update(b"\x00");
+        }
+
+        // Always include input content, normalized to its batch form
         hasher.update(b"input:");
-        if let Ok(json) = serde_json::to_string(&payload.input) {
+        let input = match &payload.input {
+            EmbeddingInput::Text(text) => serde_json::to_string(&[text]),
+            EmbeddingInput::Tokens(tokens) => serde_json::to_string(&[tokens]),
+            other => serde_json::to_string(other),
+        };
+        if let Ok(json) = input {
             hasher.update(json.as_bytes());
         }
 
@@ -894,4 +1043,71 @@ mod tests {
         assert_ne!(key_a, key_unscoped);
         assert_ne!(key_b, key_unscoped);
     }
+
+    fn embedding_payload(input: EmbeddingInput) -> CreateEmbeddingPayload {
+        CreateEmbeddingPayload {
+            input,
+            model: "text-embedding-3-small".to_string(),
+            encoding_format: None,
+            dimensions: None,
+            user: None,
+            provider: None,
+            input_type: None,
+            sovereignty_requirements: None,
+        }
+    }
+
+    #[test]
+    fn test_embeddings_cache_key_normalizes_input() {
+        let tenant = CacheTenantScope::unscoped();
+        let model = "text-embedding-3-small";
+
+        let single = embedding_payload(EmbeddingInput::Text("hello".to_string()));
+        let batch = embedding_payload(EmbeddingInput::TextArray(vec!["hello".to_string()]));
+        assert_eq!(
+            CacheKeys::embeddings_cache(&single, model, &tenant),
+            CacheKeys::embeddings_cache(&batch, model, &tenant)
+        );
+
+        let tokens = embedding_payload(EmbeddingInput::Tokens(vec![1.0, 2.0]));
+        let token_batch = embedding_payload(EmbeddingInput::TokenArrays(vec![vec![1.0, 2.0]]));
+        assert_eq!(
+            CacheKeys::embeddings_cache(&tokens, model, &tenant),
+            CacheKeys::embeddings_cache(&token_batch, model, &tenant)
+        );
+
+        let mut float = single.clone();
+        float.encoding_format = Some(EncodingFormat::Float);
+        assert_eq!(
+            CacheKeys::embeddings_cache(&single, model, &tenant),
+            CacheKeys::embeddings_cache(&float, model, &tenant)
+        );
+    }
+
+    #[test]
+    fn test_embeddings_cache_key_distinguishes_output_shape() {
+        let tenant = CacheTenantScope::unscoped();
+        let model = "text-embedding-3-small";
+        let base = embedding_payload(EmbeddingInput::Text("hello".to_string()));
+        let base_key = CacheKeys::embeddings_cache(&base, model, &tenant);
+
+        let mut dims = base.clone();
+        dims.dimensions = Some(256);
+        let mut base64 = base.clone();
+        base64.encoding_format = Some(EncodingFormat::Base64);
+        let mut query = base.clone();
+        query.input_type = Some("search_query".to_string());
+
+        for variant in [&dims, &base64, &query] {
+            assert_ne!(
+                base_key,
+                CacheKeys::embeddings_cache(variant, model, &tenant)
+            );
+        }
+        assert_ne!(
+            base_key,
+            CacheKeys::embeddings_cache(&base, "text-embedding-3-large", &tenant)
+        );
+        assert!(base_key.starts_with("gw:embeddings:"));
+    }
 }
@@ -1,4 +1,5 @@
 mod embedding_service;
+mod embeddings_cache;
 mod error;
 mod keys;
 mod memory;
@@ -6,6 +7,9 @@ mod memory;
 mod redis;
 mod response_cache;
 mod semantic_cache;
+mod stream_replay;
+mod tiered;
+mod token_bucket;
 mod traits;
 pub mod vector_store;
 
@@ -16,12 +20,18 @@ pub mod vector_store;
 ))]
 pub use embedding_service::EmbeddingError;
 pub use embedding_service::EmbeddingService;
+pub use embeddings_cache::EmbeddingsCache;
 pub use keys::{CacheKeys, CacheTenantScope};
 pub use memory::MemoryCache;
 #[cfg(feature = "redis")]
 pub use redis::RedisCache;
 pub use response_cache::{CacheLookupResult, ResponseCache};
-pub use semantic_cache::{SemanticCache, SemanticLookupResult, StoreParams};
+pub use semantic_cache::{
+    SIMILARITY_BUCKETS, SemanticCache, SemanticCacheError, SemanticCacheStats,
+    SemanticLookupResult, StoreParams,
+};
+pub use tiered::TieredCache;
+pub use token_bucket::{TokenBucketLimiter, TokenBucketState};
 #[cfg(feature = "sso")]
 pub use traits::CacheExt;
 pub use traits::{BudgetCheckParams, Cache, RateLimitCheckParams, RateLimitResult};
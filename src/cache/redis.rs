@@ -2,22 +2,31 @@ use std::time::Duration;
 
 use async_trait::async_trait;
 use redis::{
-    ConnectionInfo, IntoConnectionInfo, Value, aio::MultiplexedConnection, cluster::ClusterClient,
+    ConnectionInfo, ErrorKind, IntoConnectionInfo, RedisConnectionInfo, RedisError, RedisResult,
+    Value,
+    aio::MultiplexedConnection,
+    cluster::ClusterClient,
     cluster_async::ClusterConnection,
+    sentinel::{SentinelClient, SentinelNodeConnectionInfo, SentinelServerType},
 };
 
 use super::{
     error::CacheResult,
+    token_bucket::TokenBucketState,
     traits::{
         BatchLimitResult, BudgetCheckParams, BudgetReservation, Cache, RateLimitCheckParams,
         RateLimitResult,
     },
 };
-use crate::config::RedisCacheConfig;
+use crate::{compat::Mutex, config::RedisCacheConfig, observability::metrics};
 
 /// A wrapper enum for either a standalone or cluster Redis connection.
 /// Both connection types implement the `AsyncCommands` trait, so we can use
-/// the same command syntax for both.
+/// the same command syntax for both. Sentinel mode yields a standalone
+/// connection to the current master.
+///
+/// Both are multiplexed: clones share one underlying connection.
+#[derive(Clone)]
 enum RedisConn {
     Standalone(MultiplexedConnection),
     Cluster(ClusterConnection),
@@ -25,32 +34,34 @@ enum RedisConn {
 
 /// Macro to execute a Redis command on either connection type.
 /// This avoids code duplication when dispatching commands to standalone vs cluster.
+/// The result goes through [`RedisCache::observe`] so connection-level
+/// failures are recorded and the shared connection is re-established.
 macro_rules! redis_cmd {
-    ($conn:expr, $cmd:expr) => {
-        match $conn {
+    ($cache:expr, $conn:expr, $cmd:expr) => {
+        $cache.observe(match $conn {
             RedisConn::Standalone(ref mut c) => $cmd.query_async(c).await,
             RedisConn::Cluster(ref mut c) => $cmd.query_async(c).await,
-        }
+        })
     };
 }
 
 /// Macro to execute a Redis script on either connection type.
 macro_rules! redis_script {
-    ($conn:expr, $script:expr) => {
-        match $conn {
+    ($cache:expr, $conn:expr, $script:expr) => {
+        $cache.observe(match $conn {
             RedisConn::Standalone(ref mut c) => $script.invoke_async(c).await,
             RedisConn::Cluster(ref mut c) => $script.invoke_async(c).await,
-        }
+        })
     };
 }
 
 /// Macro to execute a Redis pipeline on either connection type.
 macro_rules! redis_pipe {
-    ($conn:expr, $pipe:expr) => {
-        match $conn {
+    ($cache:expr, $conn:expr, $pipe:expr) => {
+        $cache.observe(match $conn {
             RedisConn::Standalone(ref mut c) => $pipe.query_async(c).await,
             RedisConn::Cluster(ref mut c) => $pipe.query_async(c).await,
-        }
+        })
     };
 }
 
@@ -158,42 +169,102 @@ else
 end
 "#;
 
-/// Internal enum to hold either a standalone or cluster Redis client.
+/// Lua script for a token bucket shared across gateway replicas.
+/// Stores `tokens` and `ts` in a hash and refills by the time elapsed on the
+/// Redis server's clock, so replicas with skewed clocks agree.
+/// Floats are returned as strings since Lua numbers are truncated to integers.
+/// Returns: {allowed (0/1), tokens_left, wait_secs}
+const TOKEN_BUCKET_SCRIPT: &str = r#"
+local key = KEYS[1]
+local capacity = tonumber(ARGV[1])
+local rate = tonumber(ARGV[2])
+
+local time = redis.call('TIME')
+local now = tonumber(time[1]) + tonumber(time[2]) / 1000000
+
+local bucket = redis.call('HMGET', key, 'tokens', 'ts')
+local tokens = tonumber(bucket[1])
+local ts = tonumber(bucket[2])
+if tokens == nil or ts == nil then
+    tokens = capacity
+    ts = now
+end
+tokens = math.min(capacity, tokens + math.max(0, now - ts) * rate)
+
+local allowed = 0
+local wait = 0
+if tokens >= 1 then
+    tokens = tokens - 1
+    allowed = 1
+else
+    wait = (1 - tokens) / rate
+end
+
+redis.call('HSET', key, 'tokens', tostring(tokens), 'ts', tostring(now))
+-- Once full again the bucket is the same as a missing one
+redis.call('EXPIRE', key, math.ceil((capacity - tokens) / rate) + 1)
+return {allowed, tostring(tokens), tostring(wait)}
+"#;
+
+/// Internal enum to hold the Redis client for the configured topology.
 enum RedisConnection {
     Standalone(redis::Client),
     Cluster(ClusterClient),
+    /// Querying the sentinels needs `&mut`, hence the lock.
+    Sentinel(tokio::sync::Mutex<SentinelClient>),
+}
+
+impl RedisConnection {
+    fn topology(&self) -> &'static str {
+        match self {
+            RedisConnection::Standalone(_) => "standalone",
+            RedisConnection::Cluster(_) => "cluster",
+            RedisConnection::Sentinel(_) => "sentinel",
+        }
+    }
+}
+
+/// Classify a command error: `node` if one node failed, `cluster` if the
+/// deployment reports itself down, `None` for errors unrelated to
+/// availability (script errors, wrong types, ...).
+fn failure_scope(error: &RedisError) -> Option<&'static str> {
+    match error.kind() {
+        ErrorKind::ClusterDown | ErrorKind::MasterDown => Some("cluster"),
+        ErrorKind::ReadOnly | ErrorKind::BusyLoadingError | ErrorKind::TryAgain => Some("node"),
+        _ if error.is_io_error()
+            || error.is_connection_dropped()
+            || error.is_connection_refusal()
+            || error.is_timeout() =>
+        {
+            Some("node")
+        }
+        _ => None,
+    }
 }
 
 pub struct RedisCache {
     connection: RedisConnection,
+    /// Multiplexed connection shared by all commands. Established on first
+    /// use and dropped after a connection-level failure, so the next command
+    /// reconnects (and, with sentinel, re-resolves the master).
+    shared: Mutex<Option<RedisConn>>,
+    connect_timeout: Duration,
     key_prefix: String,
 }
 
 impl RedisCache {
     pub async fn from_config(config: &RedisCacheConfig) -> CacheResult<Self> {
         let connection = if let Some(cluster_config) = &config.cluster {
-            // Cluster mode: parse nodes from URL (comma-separated)
-            // e.g., "redis://host1:6379,host2:6379,host3:6379"
             let nodes: Vec<ConnectionInfo> = config
-                .url
-                .split(',')
-                .map(|s| s.trim())
-                .filter(|s| !s.is_empty())
-                .map(|s| {
-                    // Ensure each node has redis:// prefix if not present
-                    let node_url = if s.starts_with("redis://") || s.starts_with("rediss://") {
-                        s.to_string()
-                    } else {
-                        format!("redis://{}", s)
-                    };
-                    node_url.into_connection_info()
-                })
+                .node_urls(&cluster_config.nodes)
+                .into_iter()
+                .map(|url| url.into_connection_info())
                 .collect::<Result<Vec<_>, _>>()?;
 
             if nodes.is_empty() {
-                return Err(super::error::CacheError::Redis(redis::RedisError::from((
-                    redis::ErrorKind::InvalidClientConfig,
-                    "No cluster nodes specified in URL",
+                return Err(super::error::CacheError::Redis(RedisError::from((
+                    ErrorKind::InvalidClientConfig,
+                    "No cluster nodes specified",
                 ))));
             }
 
@@ -213,6 +284,31 @@ impl RedisCache {
 
             let cluster_client = builder.build()?;
             RedisConnection::Cluster(cluster_client)
+        } else if let Some(sentinel_config) = &config.sentinel {
+            let sentinels = config.node_urls(&sentinel_config.nodes);
+            if sentinels.is_empty() {
+                return Err(super::error::CacheError::Redis(RedisError::from((
+                    ErrorKind::InvalidClientConfig,
+                    "No sentinel nodes specified",
+                ))));
+            }
+
+            let master_info = SentinelNodeConnectionInfo {
+                redis_connection_info: Some(RedisConnectionInfo {
+                    db: sentinel_config.db,
+                    username: sentinel_config.master_username.clone(),
+                    password: sentinel_config.master_password.clone(),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            };
+            let client = SentinelClient::build(
+                sentinels,
+                sentinel_config.master_name.clone(),
+                Some(master_info),
+                SentinelServerType::Master,
+            )?;
+            RedisConnection::Sentinel(tokio::sync::Mutex::new(client))
         } else {
             // Standalone mode: single Redis instance
             let client = redis::Client::open(config.url.as_str())?;
@@ -221,6 +317,8 @@ impl RedisCache {
 
         Ok(Self {
             connection,
+            shared: Mutex::new(None),
+            connect_timeout: Duration::from_secs(config.connect_timeout_secs),
             key_prefix: config.key_prefix.clone(),
         })
     }
@@ -229,20 +327,81 @@ impl RedisCache {
         format!("{}{}", self.key_prefix, key)
     }
 
-    /// Get a Redis connection, either standalone or cluster.
+    /// Get the shared Redis connection, connecting first if there is none.
     async fn get_connection(&self) -> CacheResult<RedisConn> {
-        match &self.connection {
-            RedisConnection::Standalone(client) => {
-                let conn = client.get_multiplexed_async_connection().await?;
-                Ok(RedisConn::Standalone(conn))
+        if let Some(conn) = self.shared.lock().clone() {
+            return Ok(conn);
+        }
+
+        let topology = self.connection.topology();
+        let conn = match tokio::time::timeout(self.connect_timeout, self.connect()).await {
+            Ok(Ok(conn)) => conn,
+            Ok(Err(e)) => {
+                self.record_unavailable(&e);
+                return Err(e.into());
+            }
+            Err(_) => {
+                let e = RedisError::from((ErrorKind::IoError, "Timed out connecting to Redis"));
+                self.record_unavailable(&e);
+                return Err(e.into());
             }
+        };
+        metrics::set_redis_connected(topology, true);
+        tracing::debug!(topology, "Connected to Redis");
+
+        // Another task may have connected concurrently; keep whichever won.
+        Ok(self.shared.lock().get_or_insert(conn).clone())
+    }
+
+    async fn connect(&self) -> RedisResult<RedisConn> {
+        match &self.connection {
+            RedisConnection::Standalone(client) => Ok(RedisConn::Standalone(
+                client.get_multiplexed_async_connection().await?,
+            )),
             RedisConnection::Cluster(client) => {
-                let conn = client.get_async_connection().await?;
-                Ok(RedisConn::Cluster(conn))
+                Ok(RedisConn::Cluster(client.get_async_connection().await?))
             }
+            // Asks the sentinels for the current master on every connect, so
+            // reconnecting after a failover lands on the promoted replica.
+            RedisConnection::Sentinel(client) => Ok(RedisConn::Standalone(
+                client.lock().await.get_async_connection().await?,
+            )),
         }
     }
 
+    /// No node (or, for sentinel, no master) could be reached.
+    fn record_unavailable(&self, error: &RedisError) {
+        let topology = self.connection.topology();
+        metrics::record_redis_failure(topology, "cluster");
+        metrics::set_redis_connected(topology, false);
+        tracing::error!(topology, error = %error, "Redis is unavailable");
+    }
+
+    /// Inspect a command result for connection-level failures.
+    ///
+    /// Node failures (dropped connection, a node loading or demoted to
+    /// replica) discard the shared connection so the next command reconnects;
+    /// the cluster client recovers from those itself, so its connection is
+    /// only discarded when the cluster as a whole reports it is down.
+    fn observe<T>(&self, result: RedisResult<T>) -> RedisResult<T> {
+        let Err(e) = &result else {
+            return result;
+        };
+        let Some(scope) = failure_scope(e) else {
+            return result;
+        };
+
+        let topology = self.connection.topology();
+        metrics::record_redis_failure(topology, scope);
+        let is_cluster = matches!(self.connection, RedisConnection::Cluster(_));
+        if scope == "cluster" || !is_cluster {
+            metrics::set_redis_connected(topology, false);
+            self.shared.lock().take();
+        }
+        tracing::warn!(topology, scope, error = %e, "Redis command failed");
+        result
+    }
+
     // ─────────────────────────────────────────────────────────────────────────────
     // Redis Streams Operations
     // ─────────────────────────────────────────────────────────────────────────────
@@ -276,7 +435,7 @@ impl RedisCache {
             cmd.arg(*field).arg(*value);
         }
 
-        let id: String = redis_cmd!(conn, cmd)?;
+        let id: String = redis_cmd!(self, conn, cmd)?;
         Ok(id)
     }
 
@@ -294,6 +453,7 @@ impl RedisCache {
         let full_key = self.prefixed_key(key);
 
         let result: Result<(), redis::RedisError> = redis_cmd!(
+            self,
             conn,
             redis::cmd("XGROUP")
                 .arg("CREATE")
@@ -341,7 +501,7 @@ impl RedisCache {
         cmd.arg("COUNT").arg(count);
         cmd.arg("STREAMS").arg(&full_key).arg(">");
 
-        let value: Value = redis_cmd!(conn, cmd)?;
+        let value: Value = redis_cmd!(self, conn, cmd)?;
 
         // Parse XREADGROUP response: [[stream_name, [[id, [field, value, ...]]]]] or Nil
         Ok(Self::parse_xreadgroup_response(value))
@@ -364,7 +524,7 @@ impl RedisCache {
             cmd.arg(*id);
         }
 
-        let count: u64 = redis_cmd!(conn, cmd)?;
+        let count: u64 = redis_cmd!(self, conn, cmd)?;
         Ok(count)
     }
 
@@ -375,7 +535,8 @@ impl RedisCache {
         let mut conn = self.get_connection().await?;
         let full_key = self.prefixed_key(key);
 
-        let value: Value = redis_cmd!(conn, redis::cmd("XPENDING").arg(&full_key).arg(group))?;
+        let value: Value =
+            redis_cmd!(self, conn, redis::cmd("XPENDING").arg(&full_key).arg(group))?;
 
         // XPENDING returns [count, min-id, max-id, [[consumer, count], ...]]
         // First element is the total pending count
@@ -403,7 +564,7 @@ impl RedisCache {
         let mut conn = self.get_connection().await?;
         let full_key = self.prefixed_key(key);
 
-        let len: u64 = redis_cmd!(conn, redis::cmd("XLEN").arg(&full_key))?;
+        let len: u64 = redis_cmd!(self, conn, redis::cmd("XLEN").arg(&full_key))?;
 
         Ok(len)
     }
@@ -476,6 +637,7 @@ impl RedisCache {
 
         // ZADD returns the number of elements added (0 if updated, 1 if new)
         let added: i64 = redis_cmd!(
+            self,
             conn,
             redis::cmd("ZADD").arg(&full_key).arg(score).arg(member)
         )?;
@@ -484,10 +646,13 @@ impl RedisCache {
         if let Some(ttl) = ttl
             && ttl.as_secs() > 0
         {
-            let current_ttl: i64 = redis_cmd!(conn, redis::cmd("TTL").arg(&full_key))?;
+            let current_ttl: i64 = redis_cmd!(self, conn, redis::cmd("TTL").arg(&full_key))?;
             if current_ttl < 0 {
-                let _: () =
-                    redis_cmd!(conn, redis::cmd("EXPIRE").arg(&full_key).arg(ttl.as_secs()))?;
+                let _: () = redis_cmd!(
+                    self,
+                    conn,
+                    redis::cmd("EXPIRE").arg(&full_key).arg(ttl.as_secs())
+                )?;
             }
         }
 
@@ -514,7 +679,7 @@ impl RedisCache {
             cmd.arg("LIMIT").arg(0).arg(limit);
         }
 
-        let value: Value = redis_cmd!(conn, cmd)?;
+        let value: Value = redis_cmd!(self, conn, cmd)?;
 
         // Parse response: [member1, score1, member2, score2, ...]
         let mut results = Vec::new();
@@ -547,6 +712,7 @@ impl RedisCache {
         let full_key = self.prefixed_key(key);
 
         let removed: u64 = redis_cmd!(
+            self,
             conn,
             redis::cmd("ZREMRANGEBYSCORE")
                 .arg(&full_key)
@@ -556,6 +722,37 @@ impl RedisCache {
 
         Ok(removed)
     }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Token Buckets
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    /// Take a token from a bucket holding `capacity` tokens that refills at
+    /// `rate` tokens per second.
+    pub async fn take_token(
+        &self,
+        key: &str,
+        capacity: u32,
+        rate: f64,
+    ) -> CacheResult<TokenBucketState> {
+        let mut conn = self.get_connection().await?;
+        let full_key = self.prefixed_key(key);
+
+        let (allowed, tokens, wait): (i64, String, String) = redis_script!(
+            self,
+            conn,
+            redis::Script::new(TOKEN_BUCKET_SCRIPT)
+                .key(&full_key)
+                .arg(capacity)
+                .arg(rate)
+        )?;
+
+        Ok(TokenBucketState {
+            allowed: allowed == 1,
+            tokens: tokens.parse().unwrap_or(0.0),
+            wait_secs: wait.parse().unwrap_or(0.0),
+        })
+    }
 }
 
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
@@ -565,7 +762,7 @@ impl Cache for RedisCache {
         let mut conn = self.get_connection().await?;
         let full_key = self.prefixed_key(key);
 
-        let data: Option<Vec<u8>> = redis_cmd!(conn, redis::cmd("GET").arg(&full_key))?;
+        let data: Option<Vec<u8>> = redis_cmd!(self, conn, redis::cmd("GET").arg(&full_key))?;
 
         Ok(data)
     }
@@ -576,6 +773,7 @@ impl Cache for RedisCache {
 
         if ttl.as_secs() > 0 {
             let _: () = redis_cmd!(
+                self,
                 conn,
                 redis::cmd("SETEX")
                     .arg(&full_key)
@@ -583,7 +781,7 @@ impl Cache for RedisCache {
                     .arg(value)
             )?;
         } else {
-            let _: () = redis_cmd!(conn, redis::cmd("SET").arg(&full_key).arg(value))?;
+            let _: () = redis_cmd!(self, conn, redis::cmd("SET").arg(&full_key).arg(value))?;
         }
 
         Ok(())
@@ -596,6 +794,7 @@ impl Cache for RedisCache {
         // Use SET with NX (only set if not exists) and EX (expire)
         let result: Option<String> = if ttl.as_secs() > 0 {
             redis_cmd!(
+                self,
                 conn,
                 redis::cmd("SET")
                     .arg(&full_key)
@@ -605,7 +804,11 @@ impl Cache for RedisCache {
                     .arg(ttl.as_secs())
             )?
         } else {
-            redis_cmd!(conn, redis::cmd("SET").arg(&full_key).arg(value).arg("NX"))?
+            redis_cmd!(
+                self,
+                conn,
+                redis::cmd("SET").arg(&full_key).arg(value).arg("NX")
+            )?
         };
 
         // SET ... NX returns "OK" if set, nil if key exists
@@ -616,7 +819,7 @@ impl Cache for RedisCache {
         let mut conn = self.get_connection().await?;
         let full_key = self.prefixed_key(key);
 
-        let _: () = redis_cmd!(conn, redis::cmd("DEL").arg(&full_key))?;
+        let _: () = redis_cmd!(self, conn, redis::cmd("DEL").arg(&full_key))?;
         Ok(())
     }
 
@@ -632,6 +835,7 @@ impl Cache for RedisCache {
         // This prevents extending rate limit windows on every increment.
         if ttl.as_secs() > 0 {
             let result: i64 = redis_script!(
+                self,
                 conn,
                 redis::Script::new(INCR_PRESERVE_TTL_SCRIPT)
                     .key(&full_key)
@@ -640,7 +844,8 @@ impl Cache for RedisCache {
             )?;
             Ok(result)
         } else {
-            let result: i64 = redis_cmd!(conn, redis::cmd("INCRBY").arg(&full_key).arg(delta))?;
+            let result: i64 =
+                redis_cmd!(self, conn, redis::cmd("INCRBY").arg(&full_key).arg(delta))?;
             Ok(result)
         }
     }
@@ -661,6 +866,7 @@ impl Cache for RedisCache {
         let full_key = self.prefixed_key(key);
 
         let result: Vec<i64> = redis_script!(
+            self,
             conn,
             redis::Script::new(BUDGET_CHECK_SCRIPT)
                 .key(&full_key)
@@ -686,6 +892,7 @@ impl Cache for RedisCache {
         let full_key = self.prefixed_key(key);
 
         let result: Vec<i64> = redis_script!(
+            self,
             conn,
             redis::Script::new(RATE_LIMIT_SCRIPT)
                 .key(&full_key)
@@ -746,7 +953,7 @@ impl Cache for RedisCache {
         }
 
         // Execute all scripts in a single round trip
-        let results: Vec<Vec<i64>> = redis_pipe!(conn, pipe)?;
+        let results: Vec<Vec<i64>> = redis_pipe!(self, conn, pipe)?;
 
         // Parse budget results (first N results)
         let budget_results: Vec<BudgetReservation> = results
@@ -788,7 +995,7 @@ impl Cache for RedisCache {
         let full_key = self.prefixed_key(key);
 
         // SADD returns the number of elements added (0 if already exists, 1 if new)
-        let added: i64 = redis_cmd!(conn, redis::cmd("SADD").arg(&full_key).arg(member))?;
+        let added: i64 = redis_cmd!(self, conn, redis::cmd("SADD").arg(&full_key).arg(member))?;
 
         // Only set TTL if provided and key has no existing expiry
         if let Some(ttl) = ttl
@@ -796,10 +1003,13 @@ impl Cache for RedisCache {
         {
             // TTL returns -1 if key exists but has no expiry, -2 if key doesn't exist
             // After SADD, key always exists, so -1 means no expiry set
-            let current_ttl: i64 = redis_cmd!(conn, redis::cmd("TTL").arg(&full_key))?;
+            let current_ttl: i64 = redis_cmd!(self, conn, redis::cmd("TTL").arg(&full_key))?;
             if current_ttl < 0 {
-                let _: () =
-                    redis_cmd!(conn, redis::cmd("EXPIRE").arg(&full_key).arg(ttl.as_secs()))?;
+                let _: () = redis_cmd!(
+                    self,
+                    conn,
+                    redis::cmd("EXPIRE").arg(&full_key).arg(ttl.as_secs())
+                )?;
             }
         }
 
@@ -811,7 +1021,7 @@ impl Cache for RedisCache {
         let full_key = self.prefixed_key(key);
 
         // SREM returns the number of elements removed
-        let removed: i64 = redis_cmd!(conn, redis::cmd("SREM").arg(&full_key).arg(member))?;
+        let removed: i64 = redis_cmd!(self, conn, redis::cmd("SREM").arg(&full_key).arg(member))?;
 
         Ok(removed > 0)
     }
@@ -820,7 +1030,7 @@ impl Cache for RedisCache {
         let mut conn = self.get_connection().await?;
         let full_key = self.prefixed_key(key);
 
-        let members: Vec<String> = redis_cmd!(conn, redis::cmd("SMEMBERS").arg(&full_key))?;
+        let members: Vec<String> = redis_cmd!(self, conn, redis::cmd("SMEMBERS").arg(&full_key))?;
 
         Ok(members)
     }
@@ -829,7 +1039,7 @@ impl Cache for RedisCache {
         let mut conn = self.get_connection().await?;
         let full_key = self.prefixed_key(key);
 
-        let count: i64 = redis_cmd!(conn, redis::cmd("SCARD").arg(&full_key))?;
+        let count: i64 = redis_cmd!(self, conn, redis::cmd("SCARD").arg(&full_key))?;
 
         Ok(count as usize)
     }
@@ -838,7 +1048,11 @@ impl Cache for RedisCache {
         let mut conn = self.get_connection().await?;
         let full_key = self.prefixed_key(key);
 
-        let is_member: i64 = redis_cmd!(conn, redis::cmd("SISMEMBER").arg(&full_key).arg(member))?;
+        let is_member: i64 = redis_cmd!(
+            self,
+            conn,
+            redis::cmd("SISMEMBER").arg(&full_key).arg(member)
+        )?;
 
         Ok(is_member == 1)
     }
@@ -848,7 +1062,11 @@ impl Cache for RedisCache {
         let full_key = self.prefixed_key(key);
 
         // EXPIRE returns 1 if the timeout was set, 0 if key doesn't exist
-        let result: i64 = redis_cmd!(conn, redis::cmd("EXPIRE").arg(&full_key).arg(ttl.as_secs()))?;
+        let result: i64 = redis_cmd!(
+            self,
+            conn,
+            redis::cmd("EXPIRE").arg(&full_key).arg(ttl.as_secs())
+        )?;
 
         Ok(result == 1)
     }
@@ -9,8 +9,9 @@
 //!   components (model, messages, temperature, tools, etc.)
 //! - **Deterministic Only**: By default, only responses with temperature=0 are cached
 //!   to ensure reproducibility
-//! - **Non-streaming Only**: Streaming responses are not cached (would require
-//!   buffering the entire stream)
+//! - **Stream Replay**: Only non-streaming responses are stored, but a
+//!   `stream: true` request that matches one gets it replayed as SSE chunks
+//!   (see [`ResponseCache::replay_stream`])
 //! - **Size Limited**: Responses larger than `max_size_bytes` are not cached
 //!
 //! # Configuration
@@ -22,6 +23,11 @@
 //! only_deterministic = true    # Only cache temperature=0 responses
 //! max_size_bytes = 1048576     # Max response size to cache (default: 1MB)
 //!
+//! [features.response_caching.stream_replay]
+//! enabled = true               # Replay cached responses to streaming requests
+//! chunk_chars = 32             # Target chunk size in characters
+//! chunk_delay_ms = 0           # Pacing between chunks
+//!
 //! [features.response_caching.key_components]
 //! model = true                 # Include model in cache key
 //! temperature = true           # Include temperature in cache key
@@ -31,17 +37,17 @@
 
 use std::{sync::Arc, time::Duration};
 
+use axum::body::Body;
+use futures_util::StreamExt;
 use serde::{Deserialize, Serialize};
 
 use super::{
     keys::{CacheKeys, CacheTenantScope},
+    stream_replay,
     traits::{Cache, CacheExt},
 };
 use crate::{
-    api_types::{
-        CreateChatCompletionPayload, CreateCompletionPayload, CreateEmbeddingPayload,
-        CreateResponsesPayload,
-    },
+    api_types::{CreateChatCompletionPayload, CreateCompletionPayload, CreateResponsesPayload},
     config::ResponseCachingConfig,
     observability::metrics,
 };
@@ -109,8 +115,8 @@ impl ResponseCache {
             return CacheLookupResult::Bypass;
         }
 
-        // Don't cache streaming requests
-        if payload.stream {
+        // Streaming requests can only be served by replaying a cached response
+        if payload.stream && !self.config.stream_replay.enabled {
             return CacheLookupResult::Bypass;
         }
 
@@ -236,6 +242,28 @@ impl ResponseCache {
         }
     }
 
+    /// Replay a cached chat completion as an SSE stream of
+    /// `chat.completion.chunk` events, for a `stream: true` request that hit
+    /// the cache.
+    ///
+    /// Returns `None` if the cached body isn't a chat completion, in which
+    /// case the request should go to the provider.
+    pub fn replay_stream(&self, cached: &CachedResponse, include_usage: bool) -> Option<Body> {
+        let replay = &self.config.stream_replay;
+        let events =
+            stream_replay::chat_completion_events(&cached.body, include_usage, replay.chunk_chars)?;
+        let delay = Duration::from_millis(replay.chunk_delay_ms);
+        let stream = futures_util::stream::iter(events.into_iter().enumerate()).then(
+            move |(i, event)| async move {
+                if i > 0 && !delay.is_zero() {
+                    tokio::time::sleep(delay).await;
+                }
+                Ok::<_, std::convert::Infallible>(event)
+            },
+        );
+        Some(Body::from_stream(stream))
+    }
+
     /// Check if a responses API request should use the cache and look up any cached response.
     ///
     /// Similar to `lookup` but for the Responses API payload structure.
@@ -583,135 +611,6 @@ impl ResponseCache {
 
         true
     }
-
-    /// Check if an embeddings API request should use the cache and look up any cached response.
-    ///
-    /// Note: Embeddings are fully deterministic (no temperature/seed/streaming),
-    /// making them excellent candidates for caching.
-    pub async fn lookup_embeddings(
-        &self,
-        payload: &CreateEmbeddingPayload,
-        model: &str,
-        tenant: &CacheTenantScope,
-        force_refresh: bool,
-    ) -> CacheLookupResult {
-        // Force refresh bypasses cache lookup but still allows caching the response
-        if force_refresh {
-            tracing::debug!("Cache force refresh requested");
-            return CacheLookupResult::Miss;
-        }
-        // Check if caching is enabled
-        if !self.config.enabled {
-            return CacheLookupResult::Bypass;
-        }
-
-        // Embeddings don't have streaming or temperature, so no bypass checks needed
-
-        // Generate cache key
-        let cache_key = CacheKeys::embeddings_cache(payload, model, tenant);
-
-        // Look up in cache
-        match self.cache.get_json::<CachedResponse>(&cache_key).await {
-            Ok(Some(cached)) => {
-                metrics::record_cache_operation("response", "get", "hit");
-                tracing::debug!(
-                    cache_key = %cache_key,
-                    provider = %cached.provider,
-                    model = %cached.model,
-                    "Embeddings cache hit"
-                );
-                CacheLookupResult::Hit(cached)
-            }
-            Ok(None) => {
-                metrics::record_cache_operation("response", "get", "miss");
-                tracing::debug!(cache_key = %cache_key, "Embeddings cache miss");
-                CacheLookupResult::Miss
-            }
-            Err(e) => {
-                metrics::record_cache_operation("response", "get", "error");
-                tracing::warn!(
-                    cache_key = %cache_key,
-                    error = %e,
-                    "Embeddings cache lookup error, treating as miss"
-                );
-                CacheLookupResult::Miss
-            }
-        }
-    }
-
-    /// Store an embeddings API response in the cache.
-    pub async fn store_embeddings(
-        &self,
-        payload: &CreateEmbeddingPayload,
-        model: &str,
-        provider: &str,
-        tenant: &CacheTenantScope,
-        body: Vec<u8>,
-        content_type: &str,
-    ) -> bool {
-        // Check if caching is enabled
-        if !self.config.enabled {
-            return false;
-        }
-
-        // Embeddings don't have streaming or temperature, so no bypass checks needed
-
-        // Check response size
-        if body.len() > self.config.max_size_bytes {
-            tracing::debug!(
-                size = body.len(),
-                max_size = self.config.max_size_bytes,
-                "Embeddings response too large to cache"
-            );
-            return false;
-        }
-
-        // Generate cache key
-        let cache_key = CacheKeys::embeddings_cache(payload, model, tenant);
-
-        // Create cached response
-        let cached = CachedResponse {
-            body,
-            content_type: content_type.to_string(),
-            provider: provider.to_string(),
-            model: model.to_string(),
-            cached_at: chrono::Utc::now().timestamp(),
-        };
-
-        // Store in cache
-        let ttl = Duration::from_secs(self.config.ttl_secs);
-        match self.cache.set_json(&cache_key, &cached, ttl).await {
-            Ok(()) => {
-                metrics::record_cache_operation("response", "set", "success");
-                tracing::debug!(
-                    cache_key = %cache_key,
-                    provider = %provider,
-                    model = %model,
-                    size = cached.body.len(),
-                    ttl_secs = self.config.ttl_secs,
-                    "Embeddings response cached"
-                );
-                true
-            }
-            Err(e) => {
-                metrics::record_cache_operation("response", "set", "error");
-                tracing::warn!(
-                    cache_key = %cache_key,
-                    error = %e,
-                    "Failed to cache embeddings response"
-                );
-                false
-            }
-        }
-    }
-
-    /// Check if an embeddings API request is cacheable (without doing a cache lookup).
-    ///
-    /// Embeddings are always cacheable when caching is enabled, as they are
-    /// fully deterministic (no temperature, seed, or streaming).
-    pub fn is_embeddings_cacheable(&self) -> bool {
-        self.config.enabled
-    }
 }
 
 #[cfg(test)]
@@ -735,6 +634,8 @@ mod tests {
             max_size_bytes: 1024 * 1024,
             key_components: CacheKeyComponents::default(),
             semantic: None,
+            embeddings: Default::default(),
+            stream_replay: Default::default(),
         }
     }
 
@@ -785,9 +686,10 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_streaming_bypasses_cache() {
+    async fn test_streaming_bypasses_cache_without_replay() {
         let cache = create_test_cache();
-        let config = create_test_config();
+        let mut config = create_test_config();
+        config.stream_replay.enabled = false;
 
         let response_cache = ResponseCache::new(cache, config);
         let payload = create_test_payload(true, Some(0.0));
@@ -855,6 +757,55 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_streaming_request_replays_cached_response() {
+        let response_cache = ResponseCache::new(create_test_cache(), create_test_config());
+        let tenant = CacheTenantScope::unscoped();
+        let body = br#"{"id":"test","object":"chat.completion","created":1,"model":"gpt-4","choices":[{"index":0,"message":{"role":"assistant","content":"Hi there."},"finish_reason":"stop"}]}"#;
+
+        // Streamed responses aren't stored
+        let streaming = create_test_payload(true, Some(0.0));
+        assert!(
+            !response_cache
+                .store(
+                    &streaming,
+                    "gpt-4",
+                    "openai",
+                    &tenant,
+                    body.to_vec(),
+                    "application/json"
+                )
+                .await
+        );
+
+        let non_streaming = create_test_payload(false, Some(0.0));
+        assert!(
+            response_cache
+                .store(
+                    &non_streaming,
+                    "gpt-4",
+                    "openai",
+                    &tenant,
+                    body.to_vec(),
+                    "application/json"
+                )
+                .await
+        );
+
+        let CacheLookupResult::Hit(cached) = response_cache
+            .lookup(&streaming, "gpt-4", &tenant, false)
+            .await
+        else {
+            panic!("Expected cache hit for streaming request");
+        };
+        let replay = response_cache.replay_stream(&cached, false).unwrap();
+        let bytes = axum::body::to_bytes(replay, usize::MAX).await.unwrap();
+        let sse = std::str::from_utf8(&bytes).unwrap();
+        assert!(sse.contains(r#""object":"chat.completion.chunk""#));
+        assert!(sse.contains(r#""content":"Hi there.""#));
+        assert!(sse.ends_with("data: [DONE]\n\n"));
+    }
+
     #[tokio::test]
     async fn test_force_refresh_bypasses_cache() {
         let cache = create_test_cache();
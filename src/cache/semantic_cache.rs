@@ -11,6 +11,14 @@
 //! 3. **Background Embedding**: Embeddings are generated in background tasks to avoid
 //!    blocking response delivery
 //!
+//! # Tenant Isolation
+//!
+//! Embeddings are tagged with the requesting organization and project, and
+//! searches only consider entries from the same org/project, so one tenant's
+//! cached answers never serve another. Per-organization lookup counters back
+//! the admin `semantic-cache` endpoints, which can also purge an
+//! organization's entries.
+//!
 //! # Configuration
 //!
 //! ```toml
@@ -63,6 +71,35 @@ pub enum SemanticLookupResult {
     Bypass,
 }
 
+/// Lower bounds of the similarity buckets semantic hits are counted in.
+pub const SIMILARITY_BUCKETS: [f64; 6] = [0.0, 0.80, 0.85, 0.90, 0.95, 0.98];
+
+/// How long per-organization counters survive without being updated.
+const STATS_TTL: Duration = Duration::from_secs(30 * 86400);
+
+/// Lookup counters for one organization's semantic cache.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SemanticCacheStats {
+    pub exact_hits: u64,
+    pub semantic_hits: u64,
+    pub misses: u64,
+    /// Semantic hits per similarity bucket, in [`SIMILARITY_BUCKETS`] order.
+    pub similarity_buckets: Vec<u64>,
+}
+
+impl SemanticCacheStats {
+    /// Total lookups counted.
+    pub fn lookups(&self) -> u64 {
+        self.exact_hits + self.semantic_hits + self.misses
+    }
+
+    /// Fraction of lookups served from cache, if there were any.
+    pub fn hit_rate(&self) -> Option<f64> {
+        let lookups = self.lookups();
+        (lookups > 0).then(|| (self.exact_hits + self.semantic_hits) as f64 / lookups as f64)
+    }
+}
+
 /// Background task message for embedding generation.
 #[derive(Debug)]
 struct EmbeddingTask {
@@ -242,6 +279,23 @@ impl SemanticCache {
             return SemanticLookupResult::Bypass;
         }
 
+        let result = self
+            .find_response(payload, model, key_components, tenant)
+            .await;
+        if let Some(org_id) = tenant.org_id.as_deref() {
+            record_lookup_stats(self.cache.as_ref(), org_id, &result).await;
+        }
+        result
+    }
+
+    /// Exact then semantic search for a cached response.
+    async fn find_response(
+        &self,
+        payload: &CreateChatCompletionPayload,
+        model: &str,
+        key_components: &crate::config::CacheKeyComponents,
+        tenant: &CacheTenantScope,
+    ) -> SemanticLookupResult {
         // Generate exact cache key
         let cache_key = CacheKeys::response_cache(payload, model, key_components, tenant);
 
@@ -487,6 +541,67 @@ impl SemanticCache {
             .map_err(SemanticCacheError::VectorStore)
     }
 
+    /// List an organization's unexpired entries, newest first.
+    pub async fn list_organization_entries(
+        &self,
+        org_id: &str,
+        limit: usize,
+    ) -> Result<Vec<VectorMetadata>, SemanticCacheError> {
+        Ok(self
+            .vector_store
+            .list_by_organization(org_id, limit)
+            .await?)
+    }
+
+    /// Count an organization's unexpired entries.
+    pub async fn count_organization_entries(
+        &self,
+        org_id: &str,
+    ) -> Result<u64, SemanticCacheError> {
+        Ok(self.vector_store.count_by_organization(org_id).await?)
+    }
+
+    /// Lookup counters for an organization since its last purge.
+    pub async fn organization_stats(
+        &self,
+        org_id: &str,
+    ) -> Result<SemanticCacheStats, SemanticCacheError> {
+        read_lookup_stats(self.cache.as_ref(), org_id).await
+    }
+
+    /// Delete all of an organization's entries and reset its counters.
+    ///
+    /// Removes the embeddings and the cached responses they point at.
+    /// Responses whose embedding was still queued when the purge ran stay in
+    /// the exact-match cache until their TTL expires. Returns the number of
+    /// entries deleted.
+    pub async fn purge_organization(&self, org_id: &str) -> Result<u64, SemanticCacheError> {
+        let cache_keys = self.vector_store.delete_by_organization(org_id).await?;
+        for cache_key in &cache_keys {
+            if let Err(e) = self.cache.delete(cache_key).await {
+                tracing::warn!(
+                    cache_key = %cache_key,
+                    error = %e,
+                    "Failed to evict cached response during semantic cache purge"
+                );
+            }
+        }
+        for field in stat_fields() {
+            let _ = self
+                .cache
+                .delete(&CacheKeys::semantic_cache_stat(org_id, &field))
+                .await;
+        }
+
+        metrics::record_cache_operation("semantic", "purge", "success");
+        tracing::info!(
+            org_id = %org_id,
+            entries = cache_keys.len(),
+            "Purged organization semantic cache"
+        );
+        Ok(cache_keys.len() as u64)
+    }
+
     /// Get the similarity threshold.
     pub fn similarity_threshold(&self) -> f64 {
         self.config.similarity_threshold
@@ -518,6 +633,71 @@ fn message_content_to_string(content: &crate::api_types::MessageContent) -> Stri
     }
 }
 
+/// Index of the [`SIMILARITY_BUCKETS`] bucket a similarity falls in.
+fn similarity_bucket(similarity: f64) -> usize {
+    SIMILARITY_BUCKETS
+        .iter()
+        .rposition(|&lower| similarity >= lower)
+        .unwrap_or(0)
+}
+
+/// Names of every per-organization counter.
+fn stat_fields() -> impl Iterator<Item = String> {
+    ["exact_hit", "semantic_hit", "miss"]
+        .into_iter()
+        .map(str::to_string)
+        .chain((0..SIMILARITY_BUCKETS.len()).map(|i| format!("similarity_{i}")))
+}
+
+/// Count a lookup outcome against an organization. Counters live in the
+/// shared cache so every gateway node contributes to the same totals.
+async fn record_lookup_stats(cache: &dyn Cache, org_id: &str, result: &SemanticLookupResult) {
+    let fields = match result {
+        SemanticLookupResult::ExactHit(_) => vec!["exact_hit".to_string()],
+        SemanticLookupResult::SemanticHit { similarity, .. } => vec![
+            "semantic_hit".to_string(),
+            format!("similarity_{}", similarity_bucket(*similarity)),
+        ],
+        SemanticLookupResult::Miss => vec!["miss".to_string()],
+        SemanticLookupResult::Bypass => return,
+    };
+    for field in fields {
+        let key = CacheKeys::semantic_cache_stat(org_id, &field);
+        if let Err(e) = cache.incr(&key, STATS_TTL).await {
+            tracing::debug!(key = %key, error = %e, "Failed to record semantic cache stat");
+        }
+    }
+}
+
+/// Read an organization's counters.
+async fn read_lookup_stats(
+    cache: &dyn Cache,
+    org_id: &str,
+) -> Result<SemanticCacheStats, SemanticCacheError> {
+    let read = |field: String| async move {
+        // Incrementing by zero reads a counter on every backend.
+        cache
+            .incr_by(
+                &CacheKeys::semantic_cache_stat(org_id, &field),
+                0,
+                STATS_TTL,
+            )
+            .await
+            .map(|v| v.max(0) as u64)
+            .map_err(|e| SemanticCacheError::Cache(e.to_string()))
+    };
+    let mut similarity_buckets = Vec::with_capacity(SIMILARITY_BUCKETS.len());
+    for i in 0..SIMILARITY_BUCKETS.len() {
+        similarity_buckets.push(read(format!("similarity_{i}")).await?);
+    }
+    Ok(SemanticCacheStats {
+        exact_hits: read("exact_hit".to_string()).await?,
+        semantic_hits: read("semantic_hit".to_string()).await?,
+        misses: read("miss".to_string()).await?,
+        similarity_buckets,
+    })
+}
+
 /// Errors that can occur during semantic caching operations.
 #[derive(Debug, thiserror::Error)]
 pub enum SemanticCacheError {
@@ -569,4 +749,55 @@ mod tests {
         ]);
         assert_eq!(message_content_to_string(&content), "First Second");
     }
+
+    #[test]
+    fn test_similarity_bucket() {
+        assert_eq!(similarity_bucket(0.5), 0);
+        assert_eq!(similarity_bucket(0.80), 1);
+        assert_eq!(similarity_bucket(0.949), 3);
+        assert_eq!(similarity_bucket(0.97), 4);
+        assert_eq!(similarity_bucket(1.0), SIMILARITY_BUCKETS.len() - 1);
+    }
+
+    #[tokio::test]
+    async fn test_lookup_stats_are_per_organization() {
+        use crate::{cache::MemoryCache, config::MemoryCacheConfig};
+
+        let cache = MemoryCache::new(&MemoryCacheConfig::default());
+        let response = || CachedResponse {
+            body: vec![],
+            content_type: "application/json".to_string(),
+            provider: "openai".to_string(),
+            model: "gpt-4".to_string(),
+            cached_at: 0,
+        };
+
+        record_lookup_stats(&cache, "org-a", &SemanticLookupResult::ExactHit(response())).await;
+        record_lookup_stats(
+            &cache,
+            "org-a",
+            &SemanticLookupResult::SemanticHit {
+                response: response(),
+                similarity: 0.96,
+            },
+        )
+        .await;
+        record_lookup_stats(&cache, "org-a", &SemanticLookupResult::Miss).await;
+        record_lookup_stats(&cache, "org-a", &SemanticLookupResult::Bypass).await;
+        record_lookup_stats(&cache, "org-b", &SemanticLookupResult::Miss).await;
+
+        let stats = read_lookup_stats(&cache, "org-a").await.unwrap();
+        assert_eq!(stats.exact_hits, 1);
+        assert_eq!(stats.semantic_hits, 1);
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.similarity_buckets, vec![0, 0, 0, 0, 1, 0]);
+        assert!((stats.hit_rate().unwrap() - 2.0 / 3.0).abs() < 1e-9);
+
+        let stats = read_lookup_stats(&cache, "org-b").await.unwrap();
+        assert_eq!(stats.lookups(), 1);
+        assert_eq!(stats.hit_rate(), Some(0.0));
+
+        let stats = read_lookup_stats(&cache, "org-c").await.unwrap();
+        assert_eq!(stats.hit_rate(), None);
+    }
 }
@@ -0,0 +1,242 @@
+//! Replay of cached chat completions as SSE streams.
+//!
+//! Converts a stored `chat.completion` body into the `chat.completion.chunk`
+//! events a provider would have streamed: a role chunk, the reasoning and
+//! content text split at word and sentence boundaries, refusals and tool
+//! calls, a finish chunk per choice, an optional usage chunk, and `[DONE]`.
+
+use bytes::Bytes;
+use serde_json::{Value, json};
+
+/// Build the SSE events for a cached chat completion body.
+///
+/// Returns `None` if the body isn't a chat completion (e.g. an entry written
+/// by an older version or another endpoint), so the caller can treat the
+/// lookup as a miss.
+pub(super) fn chat_completion_events(
+    body: &[u8],
+    include_usage: bool,
+    chunk_chars: usize,
+) -> Option<Vec<Bytes>> {
+    let completion: Value = serde_json::from_slice(body).ok()?;
+    if completion.get("object").and_then(Value::as_str) != Some("chat.completion") {
+        return None;
+    }
+    let choices = completion.get("choices")?.as_array()?;
+
+    let mut template = json!({
+        "id": completion.get("id").cloned().unwrap_or(Value::Null),
+        "object": "chat.completion.chunk",
+        "created": completion.get("created").cloned().unwrap_or(Value::Null),
+        "model": completion.get("model").cloned().unwrap_or(Value::Null),
+    });
+    if let Some(fingerprint) = completion.get("system_fingerprint") {
+        template["system_fingerprint"] = fingerprint.clone();
+    }
+    let event = |choices: Value, usage: Option<&Value>| {
+        let mut chunk = template.clone();
+        chunk["choices"] = choices;
+        if let Some(usage) = usage {
+            chunk["usage"] = usage.clone();
+        }
+        Bytes::from(format!("data: {chunk}\n\n"))
+    };
+
+    let mut events = Vec::new();
+    for choice in choices {
+        let index = choice.get("index").cloned().unwrap_or(json!(0));
+        let delta_event = |delta: Value| {
+            event(
+                json!([{ "index": index, "delta": delta, "finish_reason": null }]),
+                None,
+            )
+        };
+        let message = choice.get("message").unwrap_or(&Value::Null);
+        let role = message.get("role").cloned().unwrap_or(json!("assistant"));
+
+        events.push(delta_event(json!({ "role": role, "content": "" })));
+        if let Some(reasoning) = message.get("reasoning").and_then(Value::as_str) {
+            for piece in split_text(reasoning, chunk_chars) {
+                events.push(delta_event(json!({ "reasoning": piece })));
+            }
+        }
+        if let Some(content) = message.get("content").and_then(Value::as_str) {
+            for piece in split_text(content, chunk_chars) {
+                events.push(delta_event(json!({ "content": piece })));
+            }
+        }
+        if let Some(refusal) = message.get("refusal").and_then(Value::as_str) {
+            events.push(delta_event(json!({ "refusal": refusal })));
+        }
+        if let Some(tool_calls) = message.get("tool_calls").and_then(Value::as_array) {
+            // Arguments are short enough to send whole; clients concatenate
+            // argument deltas either way.
+            for (i, call) in tool_calls.iter().enumerate() {
+                events.push(delta_event(json!({
+                    "tool_calls": [{
+                        "index": i,
+                        "id": call.get("id"),
+                        "type": call.get("type"),
+                        "function": call.get("function"),
+                    }]
+                })));
+            }
+        }
+        events.push(event(
+            json!([{
+                "index": index,
+                "delta": {},
+                "finish_reason": choice.get("finish_reason").cloned().unwrap_or(json!("stop")),
+            }]),
+            None,
+        ));
+    }
+
+    if include_usage && let Some(usage) = completion.get("usage") {
+        events.push(event(json!([]), Some(usage)));
+    }
+    events.push(Bytes::from_static(b"data: [DONE]\n\n"));
+    Some(events)
+}
+
+/// Split text into pieces of roughly `target` characters.
+///
+/// Pieces end after the first whitespace once `target` is reached, or
+/// earlier after a sentence-ending word, so no word is split. Concatenating
+/// the pieces yields the original text.
+fn split_text(text: &str, target: usize) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    let mut end = 0;
+    let mut chars = 0;
+    for word in text.split_inclusive(char::is_whitespace) {
+        end += word.len();
+        chars += word.chars().count();
+        let ends_sentence = word.ends_with('\n') || word.trim_end().ends_with(['.', '!', '?']);
+        if chars >= target || ends_sentence {
+            pieces.push(&text[start..end]);
+            start = end;
+            chars = 0;
+        }
+    }
+    if start < text.len() {
+        pieces.push(&text[start..]);
+    }
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_events(events: &[Bytes]) -> Vec<Value> {
+        events
+            .iter()
+            .map(|e| std::str::from_utf8(e).unwrap())
+            .filter(|e| *e != "data: [DONE]\n\n")
+            .map(|e| serde_json::from_str(e.trim_start_matches("data: ").trim_end()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_split_text_preserves_content() {
+        let text = "Hello there. This is a longer sentence that should be split into pieces!\nDone";
+        let pieces = split_text(text, 16);
+
+        assert_eq!(pieces.concat(), text);
+        assert_eq!(pieces[0], "Hello there. ");
+        assert!(pieces.iter().all(|p| !p.is_empty()));
+        // No word is split across pieces
+        assert!(
+            pieces[..pieces.len() - 1]
+                .iter()
+                .all(|p| p.ends_with(char::is_whitespace))
+        );
+        assert!(split_text("", 16).is_empty());
+    }
+
+    #[test]
+    fn test_replays_content_and_usage() {
+        let body = br#"{
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 1700000000,
+            "model": "gpt-4",
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": "Hello there. How can I help you today?"},
+                "finish_reason": "stop"
+            }],
+            "usage": {"prompt_tokens": 5, "completion_tokens": 9, "total_tokens": 14}
+        }"#;
+
+        let events = chat_completion_events(body, true, 8).unwrap();
+        assert_eq!(events.last().unwrap().as_ref(), b"data: [DONE]\n\n");
+
+        let chunks = parse_events(&events);
+        assert!(
+            chunks
+                .iter()
+                .all(|c| c["object"] == "chat.completion.chunk")
+        );
+        assert!(chunks.iter().all(|c| c["id"] == "chatcmpl-1"));
+        assert_eq!(chunks[0]["choices"][0]["delta"]["role"], "assistant");
+
+        let content: String = chunks
+            .iter()
+            .filter_map(|c| c["choices"][0]["delta"]["content"].as_str())
+            .collect();
+        assert_eq!(content, "Hello there. How can I help you today?");
+
+        let finish = &chunks[chunks.len() - 2];
+        assert_eq!(finish["choices"][0]["finish_reason"], "stop");
+        let usage = chunks.last().unwrap();
+        assert_eq!(usage["choices"], json!([]));
+        assert_eq!(usage["usage"]["total_tokens"], 14);
+
+        // Without include_usage there is no usage chunk
+        let events = chat_completion_events(body, false, 8).unwrap();
+        assert!(
+            parse_events(&events)
+                .iter()
+                .all(|c| c.get("usage").is_none())
+        );
+    }
+
+    #[test]
+    fn test_replays_tool_calls() {
+        let body = br#"{
+            "id": "chatcmpl-2",
+            "object": "chat.completion",
+            "created": 1700000000,
+            "model": "gpt-4",
+            "choices": [{
+                "index": 0,
+                "message": {
+                    "role": "assistant",
+                    "content": null,
+                    "tool_calls": [{
+                        "id": "call_1",
+                        "type": "function",
+                        "function": {"name": "get_weather", "arguments": "{\"city\":\"Paris\"}"}
+                    }]
+                },
+                "finish_reason": "tool_calls"
+            }]
+        }"#;
+
+        let chunks = parse_events(&chat_completion_events(body, false, 32).unwrap());
+        assert_eq!(chunks.len(), 3);
+        let call = &chunks[1]["choices"][0]["delta"]["tool_calls"][0];
+        assert_eq!(call["index"], 0);
+        assert_eq!(call["id"], "call_1");
+        assert_eq!(call["function"]["name"], "get_weather");
+        assert_eq!(chunks[2]["choices"][0]["finish_reason"], "tool_calls");
+    }
+
+    #[test]
+    fn test_rejects_non_chat_completion() {
+        assert!(chat_completion_events(b"not json", false, 32).is_none());
+        assert!(chat_completion_events(br#"{"object":"text_completion"}"#, false, 32).is_none());
+    }
+}
@@ -0,0 +1,392 @@
+//! Two-tier cache: a small in-process LRU (L1) in front of a shared cache (L2).
+//!
+//! Reads are served from L1 when possible and fall back to L2, copying the
+//! value into L1 for at most the configured L1 TTL. Writes go through to L2
+//! before L1 is updated, and deletes evict both tiers, so this node never
+//! reads a value older than its own last write. Other nodes may serve their
+//! L1 copy until it expires.
+//!
+//! Counters, budget reservations and sets must stay globally consistent and
+//! always go to L2. The one exception is an exhausted rate limit: a fixed
+//! window only resets when it expires, so a rejection is remembered locally
+//! until then and repeated requests are rejected without an L2 round trip.
+
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use dashmap::DashMap;
+
+use super::{
+    error::{CacheError, CacheResult},
+    memory::MemoryCache,
+    traits::{
+        BatchLimitResult, BudgetCheckParams, BudgetReservation, Cache, RateLimitCheckParams,
+        RateLimitResult,
+    },
+};
+use crate::{
+    config::{CacheL1Config, MemoryCacheConfig},
+    observability::metrics,
+};
+
+/// A rate limit known to be exhausted until `until`.
+struct ExhaustedLimit {
+    limit: u32,
+    current: i64,
+    until: Instant,
+}
+
+pub struct TieredCache {
+    l1: MemoryCache,
+    l2: Arc<dyn Cache>,
+    l1_ttl: Duration,
+    max_entries: usize,
+    exhausted: DashMap<String, ExhaustedLimit>,
+}
+
+impl TieredCache {
+    pub fn new(l2: Arc<dyn Cache>, config: &CacheL1Config) -> Self {
+        Self {
+            l1: MemoryCache::new(&MemoryCacheConfig {
+                max_entries: config.max_entries,
+                ..Default::default()
+            }),
+            l2,
+            l1_ttl: Duration::from_secs(config.ttl_secs),
+            max_entries: config.max_entries,
+            exhausted: DashMap::new(),
+        }
+    }
+
+    /// TTL for a local copy of a value written with `ttl` (zero = no expiry).
+    fn l1_ttl(&self, ttl: Duration) -> Duration {
+        if ttl.is_zero() {
+            self.l1_ttl
+        } else {
+            ttl.min(self.l1_ttl)
+        }
+    }
+
+    /// The locally remembered rejection for `key`, if it is still in effect.
+    fn exhausted_limit(&self, key: &str, limit: u32) -> Option<RateLimitResult> {
+        let now = Instant::now();
+        let entry = self.exhausted.get(key)?;
+        // A changed limit may admit the request; let L2 decide.
+        if entry.until <= now || entry.limit != limit {
+            drop(entry);
+            self.exhausted.remove(key);
+            return None;
+        }
+        Some(RateLimitResult {
+            allowed: false,
+            current: entry.current,
+            limit,
+            reset_secs: (entry.until - now).as_secs().max(1),
+        })
+    }
+
+    fn remember_rate_limit(&self, key: &str, result: &RateLimitResult) {
+        if result.allowed || result.reset_secs == 0 {
+            return;
+        }
+        let now = Instant::now();
+        if self.exhausted.len() >= self.max_entries {
+            self.exhausted.retain(|_, e| e.until > now);
+            if self.exhausted.len() >= self.max_entries {
+                return;
+            }
+        }
+        self.exhausted.insert(
+            key.to_string(),
+            ExhaustedLimit {
+                limit: result.limit,
+                current: result.current,
+                until: now + Duration::from_secs(result.reset_secs),
+            },
+        );
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl Cache for TieredCache {
+    async fn get_bytes(&self, key: &str) -> CacheResult<Option<Vec<u8>>> {
+        if let Some(value) = self.l1.get_bytes(key).await? {
+            metrics::record_tiered_cache_lookup("l1", "get", "hit");
+            return Ok(Some(value));
+        }
+        metrics::record_tiered_cache_lookup("l1", "get", "miss");
+
+        let value = self.l2.get_bytes(key).await?;
+        match &value {
+            Some(value) => {
+                metrics::record_tiered_cache_lookup("l2", "get", "hit");
+                self.l1.set_bytes(key, value, self.l1_ttl).await?;
+            }
+            None => metrics::record_tiered_cache_lookup("l2", "get", "miss"),
+        }
+        Ok(value)
+    }
+
+    async fn set_bytes(&self, key: &str, value: &[u8], ttl: Duration) -> CacheResult<()> {
+        if let Err(e) = self.l2.set_bytes(key, value, ttl).await {
+            // The write may or may not have landed; don't keep a stale copy.
+            self.l1.delete(key).await?;
+            return Err(e);
+        }
+        self.l1.set_bytes(key, value, self.l1_ttl(ttl)).await
+    }
+
+    async fn set_nx(&self, key: &str, value: &[u8], ttl: Duration) -> CacheResult<bool> {
+        let set = self.l2.set_nx(key, value, ttl).await?;
+        if set {
+            self.l1.set_bytes(key, value, self.l1_ttl(ttl)).await?;
+        } else {
+            // Another writer holds the key; the local copy may predate it.
+            self.l1.delete(key).await?;
+        }
+        Ok(set)
+    }
+
+    async fn delete(&self, key: &str) -> CacheResult<()> {
+        self.l1.delete(key).await?;
+        self.l2.delete(key).await
+    }
+
+    async fn incr(&self, key: &str, ttl: Duration) -> CacheResult<i64> {
+        self.l2.incr(key, ttl).await
+    }
+
+    async fn incr_by(&self, key: &str, delta: i64, ttl: Duration) -> CacheResult<i64> {
+        self.l2.incr_by(key, delta, ttl).await
+    }
+
+    async fn incr_by_float(&self, key: &str, delta: i64, ttl: Duration) -> CacheResult<i64> {
+        self.l2.incr_by_float(key, delta, ttl).await
+    }
+
+    async fn check_and_reserve_budget(
+        &self,
+        key: &str,
+        estimated_cost: i64,
+        limit: i64,
+        ttl: Duration,
+    ) -> CacheResult<BudgetReservation> {
+        self.l2
+            .check_and_reserve_budget(key, estimated_cost, limit, ttl)
+            .await
+    }
+
+    async fn check_and_incr_rate_limit(
+        &self,
+        key: &str,
+        limit: u32,
+        window_secs: u64,
+    ) -> CacheResult<RateLimitResult> {
+        if let Some(result) = self.exhausted_limit(key, limit) {
+            metrics::record_tiered_cache_lookup("l1", "rate_limit", "hit");
+            return Ok(result);
+        }
+        metrics::record_tiered_cache_lookup("l1", "rate_limit", "miss");
+
+        let result = self
+            .l2
+            .check_and_incr_rate_limit(key, limit, window_secs)
+            .await?;
+        self.remember_rate_limit(key, &result);
+        Ok(result)
+    }
+
+    async fn check_limits_batch(
+        &self,
+        budget_checks: &[BudgetCheckParams],
+        rate_limit_checks: &[RateLimitCheckParams],
+    ) -> CacheResult<BatchLimitResult> {
+        // Answer exhausted rate limits locally and send the rest to L2 in one
+        // batch, then merge the results back into input order.
+        let mut local: Vec<Option<RateLimitResult>> = Vec::with_capacity(rate_limit_checks.len());
+        let mut remote = Vec::new();
+        for check in rate_limit_checks {
+            let result = self.exhausted_limit(&check.key, check.limit);
+            let outcome = if result.is_some() { "hit" } else { "miss" };
+            metrics::record_tiered_cache_lookup("l1", "rate_limit", outcome);
+            if result.is_none() {
+                remote.push(check.clone());
+            }
+            local.push(result);
+        }
+
+        let batch = self.l2.check_limits_batch(budget_checks, &remote).await?;
+        let mut remote_results = batch.rate_limit_results.into_iter();
+        let mut rate_limit_results = Vec::with_capacity(rate_limit_checks.len());
+        for (check, result) in rate_limit_checks.iter().zip(local) {
+            let result = match result {
+                Some(result) => result,
+                None => {
+                    let result = remote_results.next().ok_or_else(|| {
+                        CacheError::Internal(
+                            "Batch returned fewer rate limit results than checks".to_string(),
+                        )
+                    })?;
+                    self.remember_rate_limit(&check.key, &result);
+                    result
+                }
+            };
+            rate_limit_results.push(result);
+        }
+
+        Ok(BatchLimitResult {
+            budget_results: batch.budget_results,
+            rate_limit_results,
+        })
+    }
+
+    async fn set_add(&self, key: &str, member: &str, ttl: Option<Duration>) -> CacheResult<bool> {
+        self.l2.set_add(key, member, ttl).await
+    }
+
+    async fn set_remove(&self, key: &str, member: &str) -> CacheResult<bool> {
+        self.l2.set_remove(key, member).await
+    }
+
+    async fn set_members(&self, key: &str) -> CacheResult<Vec<String>> {
+        self.l2.set_members(key).await
+    }
+
+    async fn set_cardinality(&self, key: &str) -> CacheResult<usize> {
+        self.l2.set_cardinality(key).await
+    }
+
+    async fn set_is_member(&self, key: &str, member: &str) -> CacheResult<bool> {
+        self.l2.set_is_member(key, member).await
+    }
+
+    async fn set_expire(&self, key: &str, ttl: Duration) -> CacheResult<bool> {
+        // Keep the local copy from outliving the new expiry.
+        self.l1.delete(key).await?;
+        self.l2.set_expire(key, ttl).await
+    }
+
+    #[cfg(feature = "redis")]
+    fn as_redis(&self) -> Option<&super::RedisCache> {
+        self.l2.as_redis()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tiered() -> (TieredCache, Arc<MemoryCache>) {
+        let l2 = Arc::new(MemoryCache::new(&MemoryCacheConfig::default()));
+        let cache = TieredCache::new(
+            l2.clone(),
+            &CacheL1Config {
+                max_entries: 100,
+                ttl_secs: 60,
+            },
+        );
+        (cache, l2)
+    }
+
+    #[tokio::test]
+    async fn test_reads_populate_l1() {
+        let (cache, l2) = tiered();
+        l2.set_bytes("key", b"v1", Duration::from_secs(60))
+            .await
+            .unwrap();
+
+        assert_eq!(cache.get_bytes("key").await.unwrap(), Some(b"v1".to_vec()));
+
+        // Changed behind our back (another node): L1 keeps serving its copy
+        l2.set_bytes("key", b"v2", Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert_eq!(cache.get_bytes("key").await.unwrap(), Some(b"v1".to_vec()));
+        assert_eq!(cache.get_bytes("missing").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_writes_go_through_and_deletes_evict() {
+        let (cache, l2) = tiered();
+
+        cache
+            .set_bytes("key", b"v1", Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert_eq!(l2.get_bytes("key").await.unwrap(), Some(b"v1".to_vec()));
+        assert_eq!(
+            cache.l1.get_bytes("key").await.unwrap(),
+            Some(b"v1".to_vec())
+        );
+
+        // set_nx losing to an existing value drops the local copy
+        l2.set_bytes("key", b"v2", Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert!(
+            !cache
+                .set_nx("key", b"v3", Duration::from_secs(60))
+                .await
+                .unwrap()
+        );
+        assert_eq!(cache.get_bytes("key").await.unwrap(), Some(b"v2".to_vec()));
+
+        cache.delete("key").await.unwrap();
+        assert_eq!(cache.get_bytes("key").await.unwrap(), None);
+        assert_eq!(l2.get_bytes("key").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_rate_limit_is_answered_locally() {
+        let (cache, l2) = tiered();
+
+        for _ in 0..2 {
+            assert!(
+                cache
+                    .check_and_incr_rate_limit("rl", 2, 60)
+                    .await
+                    .unwrap()
+                    .allowed
+            );
+        }
+        let rejected = cache.check_and_incr_rate_limit("rl", 2, 60).await.unwrap();
+        assert!(!rejected.allowed);
+        assert!(cache.exhausted.contains_key("rl"));
+
+        // Reset L2's counter: the local rejection still stands for the window
+        l2.delete("rl").await.unwrap();
+        let batch = cache
+            .check_limits_batch(
+                &[],
+                &[
+                    RateLimitCheckParams {
+                        key: "rl".to_string(),
+                        limit: 2,
+                        window_secs: 60,
+                    },
+                    RateLimitCheckParams {
+                        key: "other".to_string(),
+                        limit: 2,
+                        window_secs: 60,
+                    },
+                ],
+            )
+            .await
+            .unwrap();
+        assert!(!batch.rate_limit_results[0].allowed);
+        assert!(batch.rate_limit_results[1].allowed);
+
+        // A raised limit goes back to L2
+        assert!(
+            cache
+                .check_and_incr_rate_limit("rl", 10, 60)
+                .await
+                .unwrap()
+                .allowed
+        );
+    }
+}
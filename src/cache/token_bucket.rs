@@ -0,0 +1,270 @@
+//! Request rate limiting with token buckets shared across gateway replicas.
+//!
+//! With `window_type = "token_bucket"`, each API key's request-per-minute
+//! limit is a bucket holding `limit` tokens that refills continuously at
+//! `limit` per minute. When the cache is Redis, the bucket lives there and is
+//! updated by a Lua script using the Redis server's clock, so every replica
+//! draws from the same bucket and clock skew between replicas doesn't matter.
+//!
+//! If Redis can't be reached the check falls back to a bucket on this node,
+//! sized at `1 / fallback_replicas` of the limit so the replicas together stay
+//! close to it. Requests are never failed just because Redis is down.
+//!
+//! Rejected requests are told to retry once their next token is due, plus up
+//! to `jitter_ratio` of that wait at random, so clients limited at the same
+//! moment don't all come back for the same replenished token.
+
+use std::{collections::HashMap, sync::Arc, time::Instant};
+
+use rand::Rng;
+
+use super::{Cache, RateLimitResult};
+use crate::{
+    compat::Mutex,
+    config::{RateLimitDefaults, RateLimitWindowType, TokenBucketConfig},
+};
+
+/// Local buckets are pruned once this many keys are tracked. Only full
+/// buckets are dropped, which are equivalent to fresh ones.
+const PRUNE_THRESHOLD: usize = 10_000;
+
+/// A bucket after taking a token from it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TokenBucketState {
+    /// Whether a token was available.
+    pub allowed: bool,
+    /// Tokens left in the bucket.
+    pub tokens: f64,
+    /// Seconds until the next token, if none was available.
+    pub wait_secs: f64,
+}
+
+struct LocalBucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Token buckets for request-per-minute limits.
+pub struct TokenBucketLimiter {
+    jitter_ratio: f64,
+    fallback_replicas: u32,
+    local: Mutex<HashMap<String, LocalBucket>>,
+}
+
+impl TokenBucketLimiter {
+    pub fn new(config: &TokenBucketConfig) -> Self {
+        Self {
+            jitter_ratio: config.jitter_ratio.clamp(0.0, 1.0),
+            fallback_replicas: config.fallback_replicas.max(1),
+            local: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Build a limiter if request limits use token buckets.
+    pub fn from_config(config: &RateLimitDefaults) -> Option<Self> {
+        matches!(config.window_type, RateLimitWindowType::TokenBucket)
+            .then(|| Self::new(&config.token_bucket))
+    }
+
+    /// Take a token from `key`'s bucket, which holds `limit` tokens and
+    /// refills at `limit` per `window_secs`.
+    pub async fn check(
+        &self,
+        cache: &Arc<dyn Cache>,
+        key: &str,
+        limit: u32,
+        window_secs: u64,
+    ) -> RateLimitResult {
+        let rate = f64::from(limit) / window_secs.max(1) as f64;
+        let (state, capacity) = match self.take_shared(cache, key, limit, rate).await {
+            Some(state) => (state, limit),
+            None if !shares_buckets(cache) => {
+                (self.take_local(key, limit, rate, Instant::now()), limit)
+            }
+            None => {
+                // Each replica enforces its share until Redis is back
+                let capacity = (limit / self.fallback_replicas).max(1);
+                let rate = rate / f64::from(self.fallback_replicas);
+                (
+                    self.take_local(key, capacity, rate, Instant::now()),
+                    capacity,
+                )
+            }
+        };
+
+        let reset_secs = if state.allowed {
+            (f64::from(capacity) - state.tokens) / rate
+        } else {
+            let jitter = rand::thread_rng().gen_range(0.0..=self.jitter_ratio);
+            state.wait_secs * (1.0 + jitter)
+        };
+        RateLimitResult {
+            allowed: state.allowed,
+            current: (f64::from(capacity) - state.tokens.floor()).max(0.0) as i64,
+            limit: capacity,
+            reset_secs: (reset_secs.ceil() as u64).max(1),
+        }
+    }
+
+    #[cfg(feature = "redis")]
+    async fn take_shared(
+        &self,
+        cache: &Arc<dyn Cache>,
+        key: &str,
+        capacity: u32,
+        rate: f64,
+    ) -> Option<TokenBucketState> {
+        let redis = cache.as_redis()?;
+        match redis.take_token(key, capacity, rate).await {
+            Ok(state) => Some(state),
+            Err(e) => {
+                tracing::warn!(
+                    key,
+                    error = %e,
+                    "Redis token bucket unavailable, falling back to a local bucket"
+                );
+                crate::observability::metrics::record_token_bucket_fallback();
+                None
+            }
+        }
+    }
+
+    #[cfg(not(feature = "redis"))]
+    async fn take_shared(
+        &self,
+        _cache: &Arc<dyn Cache>,
+        _key: &str,
+        _capacity: u32,
+        _rate: f64,
+    ) -> Option<TokenBucketState> {
+        None
+    }
+
+    /// Take a token from the bucket kept on this node.
+    fn take_local(&self, key: &str, capacity: u32, rate: f64, now: Instant) -> TokenBucketState {
+        let capacity = f64::from(capacity);
+        let refill = |bucket: &LocalBucket| {
+            let elapsed = now.saturating_duration_since(bucket.updated);
+            (bucket.tokens + elapsed.as_secs_f64() * rate).min(capacity)
+        };
+
+        let mut buckets = self.local.lock();
+        if buckets.len() >= PRUNE_THRESHOLD {
+            buckets.retain(|_, bucket| refill(bucket) < capacity);
+        }
+        let bucket = buckets.entry(key.to_string()).or_insert(LocalBucket {
+            tokens: capacity,
+            updated: now,
+        });
+        let tokens = refill(bucket);
+        bucket.updated = now;
+
+        if tokens >= 1.0 {
+            bucket.tokens = tokens - 1.0;
+            TokenBucketState {
+                allowed: true,
+                tokens: bucket.tokens,
+                wait_secs: 0.0,
+            }
+        } else {
+            bucket.tokens = tokens;
+            TokenBucketState {
+                allowed: false,
+                tokens,
+                wait_secs: (1.0 - tokens) / rate,
+            }
+        }
+    }
+}
+
+/// Whether buckets in this cache are shared with other replicas.
+fn shares_buckets(cache: &Arc<dyn Cache>) -> bool {
+    #[cfg(feature = "redis")]
+    {
+        cache.as_redis().is_some()
+    }
+    #[cfg(not(feature = "redis"))]
+    {
+        let _ = cache;
+        false
+    }
+}
+
+impl std::fmt::Debug for TokenBucketLimiter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TokenBucketLimiter")
+            .field("jitter_ratio", &self.jitter_ratio)
+            .field("fallback_replicas", &self.fallback_replicas)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::cache::MemoryCache;
+
+    fn limiter(jitter_ratio: f64, fallback_replicas: u32) -> TokenBucketLimiter {
+        TokenBucketLimiter::new(&TokenBucketConfig {
+            jitter_ratio,
+            fallback_replicas,
+        })
+    }
+
+    #[test]
+    fn test_local_bucket_refills_continuously() {
+        let limiter = limiter(0.0, 1);
+        let now = Instant::now();
+
+        // 60 per minute = one token per second, three in the bucket
+        for _ in 0..3 {
+            assert!(limiter.take_local("k", 3, 1.0, now).allowed);
+        }
+        let empty = limiter.take_local("k", 3, 1.0, now);
+        assert!(!empty.allowed);
+        assert_eq!(empty.wait_secs, 1.0);
+
+        // Half a second later half a token has come back: not enough yet
+        let state = limiter.take_local("k", 3, 1.0, now + Duration::from_millis(500));
+        assert!(!state.allowed);
+        assert_eq!(state.wait_secs, 0.5);
+
+        let state = limiter.take_local("k", 3, 1.0, now + Duration::from_secs(1));
+        assert!(state.allowed);
+
+        // Other keys have their own bucket
+        assert!(limiter.take_local("other", 3, 1.0, now).allowed);
+    }
+
+    #[tokio::test]
+    async fn test_memory_cache_uses_full_limit_locally() {
+        let limiter = limiter(0.0, 4);
+        let cache: Arc<dyn Cache> = Arc::new(MemoryCache::new(&Default::default()));
+
+        // Not a shared backend, so `fallback_replicas` doesn't apply
+        for _ in 0..8 {
+            assert!(limiter.check(&cache, "k", 8, 60).await.allowed);
+        }
+        let result = limiter.check(&cache, "k", 8, 60).await;
+        assert!(!result.allowed);
+        assert_eq!(result.current, 8);
+        // 8 per minute: the next token is due in 7.5s
+        assert_eq!(result.reset_secs, 8);
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_is_jittered_within_ratio() {
+        let limiter = limiter(0.5, 1);
+        let cache: Arc<dyn Cache> = Arc::new(MemoryCache::new(&Default::default()));
+
+        // One per minute: the next token is due in 60s
+        assert!(limiter.check(&cache, "k", 1, 60).await.allowed);
+        for _ in 0..20 {
+            let result = limiter.check(&cache, "k", 1, 60).await;
+            assert!(!result.allowed);
+            assert!((60..=90).contains(&result.reset_secs));
+        }
+    }
+}
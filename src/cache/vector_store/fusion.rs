@@ -319,6 +319,8 @@ mod tests {
             file_id: Uuid::nil(),
             chunk_index: 0,
             content: content.to_string(),
+            char_start: 0,
+            char_end: 0,
             score,
             metadata: None,
         }
@@ -445,6 +447,8 @@ mod tests {
             file_id,
             chunk_index: 5,
             content: "test content".to_string(),
+            char_start: 0,
+            char_end: 0,
             score: 0.9,
             metadata: Some(serde_json::json!({"key": "value"})),
         }];
@@ -582,6 +586,8 @@ mod tests {
             file_id: Uuid::nil(),
             chunk_index: 0,
             content: "vector version".to_string(),
+            char_start: 0,
+            char_end: 0,
             score: 0.95, // Higher score
             metadata: Some(serde_json::json!({"source": "vector"})),
         }];
@@ -592,6 +598,8 @@ mod tests {
             file_id: Uuid::nil(),
             chunk_index: 0,
             content: "keyword version".to_string(),
+            char_start: 0,
+            char_end: 0,
             score: 0.7, // Lower score
             metadata: Some(serde_json::json!({"source": "keyword"})),
         }];
@@ -8,6 +8,7 @@
 //!
 //! - [`PgvectorStore`]: PostgreSQL with pgvector extension
 //! - [`QdrantStore`]: Qdrant vector database via HTTP API
+//! - [`SqliteVectorStore`]: SQLite tables with exact search, for single-binary deployments
 //! - [`TestVectorStore`]: No-op implementation for testing
 //!
 //! # Usage
@@ -25,6 +26,8 @@ pub mod fusion;
 #[cfg(feature = "database-postgres")]
 mod pgvector;
 mod qdrant;
+#[cfg(feature = "database-sqlite")]
+mod sqlite;
 #[cfg(test)]
 mod test;
 
@@ -39,6 +42,8 @@ pub use fusion::{HybridSearchConfig, RrfConfig};
 pub use pgvector::PgvectorStore;
 pub use qdrant::QdrantStore;
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "database-sqlite")]
+pub use sqlite::SqliteVectorStore;
 // Re-export only when database features are available, since consumers
 // (route-level integration tests) require a database to construct the app context.
 #[cfg(all(test, any(feature = "database-sqlite", feature = "database-postgres")))]
@@ -228,6 +233,10 @@ pub struct ChunkSearchResult {
     pub chunk_index: i32,
     /// The actual text content of the chunk
     pub content: String,
+    /// Character offset of the chunk in the original document
+    pub char_start: i32,
+    /// Character end offset of the chunk in the original document
+    pub char_end: i32,
     /// Similarity score (0.0 to 1.0, higher is more similar)
     pub score: f64,
     /// Optional additional metadata
@@ -359,6 +368,30 @@ pub trait VectorBackend: Send + Sync {
     /// Check if the backend is healthy and available.
     async fn health_check(&self) -> VectorStoreResult<()>;
 
+    /// List an organization's unexpired semantic cache entries, newest first.
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - Organization whose entries to list
+    /// * `limit` - Maximum number of entries to return
+    async fn list_by_organization(
+        &self,
+        organization_id: &str,
+        limit: usize,
+    ) -> VectorStoreResult<Vec<VectorMetadata>>;
+
+    /// Count an organization's unexpired semantic cache entries.
+    async fn count_by_organization(&self, organization_id: &str) -> VectorStoreResult<u64>;
+
+    /// Delete all of an organization's semantic cache entries, expired or not.
+    ///
+    /// # Returns
+    ///
+    /// The exact cache keys of the deleted entries, so the caller can evict
+    /// the cached responses they pointed at.
+    async fn delete_by_organization(&self, organization_id: &str)
+    -> VectorStoreResult<Vec<String>>;
+
     // ========================================================================
     // RAG VectorStore Chunk Operations
     // ========================================================================
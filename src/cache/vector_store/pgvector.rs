@@ -217,6 +217,16 @@ impl PgvectorStore {
             .await
             .map_err(|e| VectorStoreError::Database(e.to_string()))?;
 
+        // Create index on organization_id for per-org inspection and purges
+        let org_idx = format!(
+            "CREATE INDEX IF NOT EXISTS {}_org_idx ON {} (organization_id)",
+            self.table_name, self.table_name
+        );
+        sqlx::query(&org_idx)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| VectorStoreError::Database(e.to_string()))?;
+
         // Create the RAG vector store chunks table
         // Includes content_tsvector column for full-text search (hybrid search)
         // The processing_version column enables atomic shadow-copy updates:
@@ -990,6 +1000,165 @@ impl VectorBackend for PgvectorStore {
         }
     }
 
+    #[instrument(
+        skip(self),
+        fields(backend = "pgvector", operation = "list_by_organization")
+    )]
+    async fn list_by_organization(
+        &self,
+        organization_id: &str,
+        limit: usize,
+    ) -> VectorStoreResult<Vec<VectorMetadata>> {
+        #[derive(sqlx::FromRow)]
+        struct MetadataRow {
+            cache_key: String,
+            model: String,
+            organization_id: Option<String>,
+            project_id: Option<String>,
+            created_at: i64,
+            ttl_secs: i64,
+        }
+
+        let start = Instant::now();
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        let query = format!(
+            r#"
+            SELECT cache_key, model, organization_id, project_id, created_at, ttl_secs
+            FROM {}
+            WHERE organization_id = $1 AND expires_at > $2
+            ORDER BY created_at DESC
+            LIMIT $3
+            "#,
+            self.table_name
+        );
+        let result = sqlx::query_as::<_, MetadataRow>(&query)
+            .bind(organization_id)
+            .bind(now)
+            .bind(limit as i64)
+            .fetch_all(&self.pool)
+            .await;
+
+        let duration = start.elapsed().as_secs_f64();
+        match result {
+            Ok(rows) => {
+                record_vector_store_operation(
+                    "pgvector",
+                    "list",
+                    "success",
+                    duration,
+                    rows.len() as u32,
+                );
+                Ok(rows
+                    .into_iter()
+                    .map(|row| VectorMetadata {
+                        cache_key: row.cache_key,
+                        model: row.model,
+                        organization_id: row.organization_id,
+                        project_id: row.project_id,
+                        created_at: row.created_at,
+                        ttl_secs: row.ttl_secs as u64,
+                    })
+                    .collect())
+            }
+            Err(e) => {
+                record_vector_store_operation("pgvector", "list", "error", duration, 0);
+                warn!(
+                    stage = "vector_operation_completed",
+                    backend = "pgvector",
+                    operation = "list_by_organization",
+                    status = "error",
+                    error = %e,
+                    "Vector list operation failed"
+                );
+                Err(VectorStoreError::Database(e.to_string()))
+            }
+        }
+    }
+
+    #[instrument(
+        skip(self),
+        fields(backend = "pgvector", operation = "count_by_organization")
+    )]
+    async fn count_by_organization(&self, organization_id: &str) -> VectorStoreResult<u64> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        let query = format!(
+            "SELECT COUNT(*) FROM {} WHERE organization_id = $1 AND expires_at > $2",
+            self.table_name
+        );
+        let count: i64 = sqlx::query_scalar(&query)
+            .bind(organization_id)
+            .bind(now)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| VectorStoreError::Database(e.to_string()))?;
+        Ok(count as u64)
+    }
+
+    #[instrument(
+        skip(self),
+        fields(backend = "pgvector", operation = "delete_by_organization")
+    )]
+    async fn delete_by_organization(
+        &self,
+        organization_id: &str,
+    ) -> VectorStoreResult<Vec<String>> {
+        let start = Instant::now();
+        let query = format!(
+            "DELETE FROM {} WHERE organization_id = $1 RETURNING cache_key",
+            self.table_name
+        );
+        let result = sqlx::query_scalar::<_, String>(&query)
+            .bind(organization_id)
+            .fetch_all(&self.pool)
+            .await;
+
+        let duration = start.elapsed().as_secs_f64();
+        let duration_ms = (duration * 1000.0) as u64;
+        match result {
+            Ok(cache_keys) => {
+                let count = cache_keys.len();
+                record_vector_store_operation(
+                    "pgvector",
+                    "delete",
+                    "success",
+                    duration,
+                    count as u32,
+                );
+                info!(
+                    stage = "vector_operation_completed",
+                    backend = "pgvector",
+                    operation = "delete_by_organization",
+                    status = "success",
+                    duration_ms = duration_ms,
+                    item_count = count,
+                    "Organization vectors deleted"
+                );
+                otel_span_ok!();
+                Ok(cache_keys)
+            }
+            Err(e) => {
+                record_vector_store_operation("pgvector", "delete", "error", duration, 0);
+                warn!(
+                    stage = "vector_operation_completed",
+                    backend = "pgvector",
+                    operation = "delete_by_organization",
+                    status = "error",
+                    duration_ms = duration_ms,
+                    error = %e,
+                    "Organization vectors delete failed"
+                );
+                otel_span_error!("Delete by organization failed: {}", e);
+                Err(VectorStoreError::Database(e.to_string()))
+            }
+        }
+    }
+
     fn dimensions(&self) -> usize {
         self.dimensions
     }
@@ -1718,6 +1887,8 @@ impl VectorBackend for PgvectorStore {
                 file_id,
                 chunk_index,
                 content,
+                char_start,
+                char_end,
                 metadata::TEXT,
                 (embedding {op} $1::vector) as distance
             FROM {}
@@ -1741,6 +1912,8 @@ impl VectorBackend for PgvectorStore {
             file_id: Uuid,
             chunk_index: i32,
             content: String,
+            char_start: i32,
+            char_end: i32,
             metadata: Option<String>,
             distance: f64,
         }
@@ -1785,6 +1958,8 @@ impl VectorBackend for PgvectorStore {
                         file_id: row.file_id,
                         chunk_index: row.chunk_index,
                         content: row.content,
+                        char_start: row.char_start,
+                        char_end: row.char_end,
                         // Convert raw distance to normalized similarity (0.0-1.0)
                         score: self.distance_to_similarity(row.distance),
                         metadata: row.metadata.and_then(|s| serde_json::from_str(&s).ok()),
@@ -1947,6 +2122,8 @@ impl VectorBackend for PgvectorStore {
                 file_id,
                 chunk_index,
                 content,
+                char_start,
+                char_end,
                 metadata::TEXT,
                 ts_rank_cd(content_tsvector, websearch_to_tsquery('english', $1)) as rank
             FROM {}
@@ -1969,6 +2146,8 @@ impl VectorBackend for PgvectorStore {
             file_id: Uuid,
             chunk_index: i32,
             content: String,
+            char_start: i32,
+            char_end: i32,
             metadata: Option<String>,
             rank: f32,
         }
@@ -2013,6 +2192,8 @@ impl VectorBackend for PgvectorStore {
                         file_id: row.file_id,
                         chunk_index: row.chunk_index,
                         content: row.content,
+                        char_start: row.char_start,
+                        char_end: row.char_end,
                         // Normalize rank to 0-1 range: rank / (1 + rank)
                         // This maps [0, ∞) to [0, 1) with good spread for typical ranks
                         score: (row.rank as f64) / (1.0 + row.rank as f64),
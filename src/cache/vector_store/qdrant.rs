@@ -162,6 +162,10 @@ impl QdrantStore {
             .await?;
         self.create_payload_index(&self.qdrant_collection_name, "expires_at", "integer")
             .await?;
+        self.create_payload_index(&self.qdrant_collection_name, "organization_id", "keyword")
+            .await?;
+        self.create_payload_index(&self.qdrant_collection_name, "created_at", "integer")
+            .await?;
 
         // Initialize chunks index
         self.initialize_qdrant_collection(&self.qdrant_chunks_collection_name)
@@ -291,6 +295,79 @@ impl QdrantStore {
         Ok(())
     }
 
+    /// POST to a points endpoint of the semantic cache collection and return
+    /// the response's `result`.
+    async fn post_cache_points(
+        &self,
+        action: &str,
+        body: &serde_json::Value,
+    ) -> VectorStoreResult<serde_json::Value> {
+        let resp = self
+            .request(
+                reqwest::Method::POST,
+                &format!(
+                    "/collections/{}/points/{}",
+                    self.qdrant_collection_name, action
+                ),
+            )
+            .query(&[("wait", "true")])
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| VectorStoreError::Http(e.to_string()))?;
+
+        if !resp.status().is_success() {
+            let error_text = resp.text().await.unwrap_or_default();
+            return Err(VectorStoreError::Database(format!(
+                "Failed to {} points: {}",
+                action, error_text
+            )));
+        }
+
+        let mut body: serde_json::Value = resp
+            .json()
+            .await
+            .map_err(|e| VectorStoreError::Serialization(e.to_string()))?;
+        Ok(body["result"].take())
+    }
+
+    /// Filter matching an organization's semantic cache entries, optionally
+    /// only the unexpired ones.
+    fn organization_filter(organization_id: &str, live_only: bool) -> serde_json::Value {
+        let mut must = vec![serde_json::json!({
+            "key": "organization_id",
+            "match": { "value": organization_id }
+        })];
+        if live_only {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as i64;
+            must.push(serde_json::json!({
+                "key": "expires_at",
+                "range": { "gt": now }
+            }));
+        }
+        serde_json::json!({ "must": must })
+    }
+
+    /// Extract metadata from the points of a scroll result.
+    fn scroll_metadata(result: &serde_json::Value) -> Vec<VectorMetadata> {
+        result["points"]
+            .as_array()
+            .map(|points| {
+                points
+                    .iter()
+                    .filter_map(|p| {
+                        let payload: HashMap<String, serde_json::Value> =
+                            serde_json::from_value(p.get("payload")?.clone()).ok()?;
+                        Self::payload_to_metadata(&payload)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     /// Convert metadata to Qdrant payload format.
     fn metadata_to_payload(
         metadata: &VectorMetadata,
@@ -1263,6 +1340,152 @@ impl VectorBackend for QdrantStore {
         Ok(count)
     }
 
+    #[instrument(
+        skip(self),
+        fields(backend = "qdrant", operation = "list_by_organization")
+    )]
+    async fn list_by_organization(
+        &self,
+        organization_id: &str,
+        limit: usize,
+    ) -> VectorStoreResult<Vec<VectorMetadata>> {
+        let start = Instant::now();
+        let body = serde_json::json!({
+            "filter": Self::organization_filter(organization_id, true),
+            "limit": limit,
+            "with_payload": true,
+            "with_vector": false,
+            "order_by": { "key": "created_at", "direction": "desc" }
+        });
+        let result = self.post_cache_points("scroll", &body).await;
+
+        let duration = start.elapsed().as_secs_f64();
+        match result {
+            Ok(result) => {
+                let entries = Self::scroll_metadata(&result);
+                record_vector_store_operation(
+                    "qdrant",
+                    "list",
+                    "success",
+                    duration,
+                    entries.len() as u32,
+                );
+                Ok(entries)
+            }
+            Err(e) => {
+                record_vector_store_operation("qdrant", "list", "error", duration, 0);
+                warn!(
+                    stage = "vector_operation_completed",
+                    backend = "qdrant",
+                    operation = "list_by_organization",
+                    status = "error",
+                    error = %e,
+                    "Vector list operation failed"
+                );
+                Err(e)
+            }
+        }
+    }
+
+    #[instrument(
+        skip(self),
+        fields(backend = "qdrant", operation = "count_by_organization")
+    )]
+    async fn count_by_organization(&self, organization_id: &str) -> VectorStoreResult<u64> {
+        let body = serde_json::json!({
+            "filter": Self::organization_filter(organization_id, true),
+            "exact": true
+        });
+        let result = self.post_cache_points("count", &body).await?;
+        result["count"].as_u64().ok_or_else(|| {
+            VectorStoreError::Serialization("count response missing count".to_string())
+        })
+    }
+
+    #[instrument(
+        skip(self),
+        fields(backend = "qdrant", operation = "delete_by_organization")
+    )]
+    async fn delete_by_organization(
+        &self,
+        organization_id: &str,
+    ) -> VectorStoreResult<Vec<String>> {
+        let start = Instant::now();
+        let filter = Self::organization_filter(organization_id, false);
+
+        let result = async {
+            // Collect the cache keys first; the delete itself doesn't return payloads.
+            let mut cache_keys = Vec::new();
+            let mut offset = serde_json::Value::Null;
+            loop {
+                let mut body = serde_json::json!({
+                    "filter": filter,
+                    "limit": 1000,
+                    "with_payload": ["cache_key", "model", "created_at", "ttl_secs"],
+                    "with_vector": false
+                });
+                if !offset.is_null() {
+                    body["offset"] = offset;
+                }
+                let mut page = self.post_cache_points("scroll", &body).await?;
+                cache_keys.extend(
+                    Self::scroll_metadata(&page)
+                        .into_iter()
+                        .map(|m| m.cache_key),
+                );
+                offset = page["next_page_offset"].take();
+                if offset.is_null() {
+                    break;
+                }
+            }
+
+            self.post_cache_points("delete", &serde_json::json!({ "filter": filter }))
+                .await?;
+            Ok::<_, VectorStoreError>(cache_keys)
+        }
+        .await;
+
+        let duration = start.elapsed().as_secs_f64();
+        let duration_ms = (duration * 1000.0) as u64;
+        match result {
+            Ok(cache_keys) => {
+                let count = cache_keys.len();
+                record_vector_store_operation(
+                    "qdrant",
+                    "delete",
+                    "success",
+                    duration,
+                    count as u32,
+                );
+                info!(
+                    stage = "vector_operation_completed",
+                    backend = "qdrant",
+                    operation = "delete_by_organization",
+                    status = "success",
+                    duration_ms = duration_ms,
+                    item_count = count,
+                    "Organization vectors deleted"
+                );
+                otel_span_ok!();
+                Ok(cache_keys)
+            }
+            Err(e) => {
+                record_vector_store_operation("qdrant", "delete", "error", duration, 0);
+                warn!(
+                    stage = "vector_operation_completed",
+                    backend = "qdrant",
+                    operation = "delete_by_organization",
+                    status = "error",
+                    duration_ms = duration_ms,
+                    error = %e,
+                    "Organization vectors delete failed"
+                );
+                otel_span_error!("Delete by organization failed: {}", e);
+                Err(e)
+            }
+        }
+    }
+
     fn dimensions(&self) -> usize {
         self.dimensions
     }
@@ -2294,6 +2517,8 @@ impl VectorBackend for QdrantStore {
                     file_id: payload.get("file_id")?.as_str()?.parse().ok()?,
                     chunk_index: payload.get("chunk_index")?.as_i64()? as i32,
                     content: payload.get("content")?.as_str()?.to_string(),
+                    char_start: payload.get("char_start")?.as_i64()? as i32,
+                    char_end: payload.get("char_end")?.as_i64()? as i32,
                     // Convert Qdrant score to normalized similarity (0.0-1.0)
                     score: self.score_to_similarity(r.score),
                     metadata: payload.get("metadata").cloned(),
@@ -2539,6 +2764,8 @@ impl VectorBackend for QdrantStore {
                     file_id: payload.get("file_id")?.as_str()?.parse().ok()?,
                     chunk_index: payload.get("chunk_index")?.as_i64()? as i32,
                     content: payload.get("content")?.as_str()?.to_string(),
+                    char_start: payload.get("char_start")?.as_i64()? as i32,
+                    char_end: payload.get("char_end")?.as_i64()? as i32,
                     // Uniform score since Qdrant text search doesn't rank results
                     score: 0.5,
                     metadata: payload.get("metadata").cloned(),
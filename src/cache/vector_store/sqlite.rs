@@ -0,0 +1,1367 @@
+//! SQLite implementation of the VectorBackend trait.
+//!
+//! Stores embeddings in the primary SQLite database so semantic caching and
+//! file search work in single-binary deployments without pgvector or Qdrant.
+//!
+//! Embeddings are persisted as little-endian `f32` BLOBs. Searches narrow the
+//! candidate set in SQL (expiry, model, tenant, vector store, file and
+//! attribute filters) and then score the remaining rows with an exact scan,
+//! keeping only the best `limit` matches in memory. Keyword search uses an
+//! FTS5 index over chunk content ranked with BM25.
+//!
+//! Exact scans grow linearly with the number of candidate rows, which is fine
+//! for the corpus sizes typical of minimal deployments. Larger installations
+//! should use pgvector or Qdrant.
+
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use futures::TryStreamExt;
+use sqlx::SqlitePool;
+use tracing::{debug, info, instrument, warn};
+use uuid::Uuid;
+
+use super::{
+    ChunkFilter, ChunkSearchResult, ChunkWithEmbedding, HybridSearchConfig, StoredChunk,
+    VectorBackend, VectorMetadata, VectorSearchResult, VectorStoreError, VectorStoreResult,
+    VectorTenantFilter, fusion::fuse_results_limited,
+};
+use crate::{
+    config::DistanceMetric,
+    models::{
+        AttributeFilter, ComparisonFilter, ComparisonOperator, CompoundFilter, FilterValue,
+        LogicalOperator,
+    },
+    observability::{metrics::record_vector_store_operation, otel_span_error, otel_span_ok},
+};
+
+const BACKEND: &str = "sqlite";
+
+/// SQLite implementation of VectorStore.
+pub struct SqliteVectorStore {
+    pool: SqlitePool,
+    /// Table name for semantic cache embeddings
+    table_name: String,
+    /// Table name for RAG vector store chunks
+    chunks_table_name: String,
+    /// FTS5 index over chunk content, used for keyword search
+    fts_table_name: String,
+    dimensions: usize,
+    /// Distance metric for similarity search
+    distance_metric: DistanceMetric,
+}
+
+impl SqliteVectorStore {
+    /// Create a new SQLite vector store.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - SQLite connection pool (normally the gateway's primary database)
+    /// * `table_name` - Table name for semantic cache embeddings; chunks are
+    ///   stored in `{table_name}_chunks`
+    /// * `dimensions` - Embedding vector dimensions
+    /// * `distance_metric` - Distance metric for similarity search
+    pub fn new(
+        pool: SqlitePool,
+        table_name: String,
+        dimensions: usize,
+        distance_metric: DistanceMetric,
+    ) -> Self {
+        let chunks_table_name = format!("{}_chunks", table_name);
+        let fts_table_name = format!("{}_chunks_fts", table_name);
+        Self {
+            pool,
+            table_name,
+            chunks_table_name,
+            fts_table_name,
+            dimensions,
+            distance_metric,
+        }
+    }
+
+    /// Create the embeddings table, chunks table and FTS5 index.
+    ///
+    /// This should be called once during application startup.
+    #[instrument(skip(self), fields(backend = "sqlite", operation = "initialize"))]
+    pub async fn initialize(&self) -> VectorStoreResult<()> {
+        let start = Instant::now();
+        info!(
+            stage = "vector_operation_started",
+            backend = BACKEND,
+            operation = "initialize",
+            table_name = %self.table_name,
+            chunks_table_name = %self.chunks_table_name,
+            dimensions = self.dimensions,
+            "Starting SQLite vector store initialization"
+        );
+
+        let table = &self.table_name;
+        let chunks = &self.chunks_table_name;
+        let fts = &self.fts_table_name;
+
+        let statements = [
+            format!(
+                r#"
+                CREATE TABLE IF NOT EXISTS {table} (
+                    id TEXT PRIMARY KEY,
+                    embedding BLOB NOT NULL,
+                    cache_key TEXT NOT NULL,
+                    model TEXT NOT NULL,
+                    organization_id TEXT,
+                    project_id TEXT,
+                    created_at INTEGER NOT NULL,
+                    ttl_secs INTEGER NOT NULL,
+                    expires_at INTEGER NOT NULL
+                )
+                "#
+            ),
+            format!("CREATE INDEX IF NOT EXISTS {table}_expires_idx ON {table} (expires_at)"),
+            format!("CREATE INDEX IF NOT EXISTS {table}_model_idx ON {table} (model)"),
+            format!("CREATE INDEX IF NOT EXISTS {table}_org_idx ON {table} (organization_id)"),
+            // `seq` is the stable integer rowid the external-content FTS5 table
+            // points at. Chunk IDs stay UUIDs like the other backends.
+            format!(
+                r#"
+                CREATE TABLE IF NOT EXISTS {chunks} (
+                    seq INTEGER PRIMARY KEY,
+                    id TEXT NOT NULL UNIQUE,
+                    vector_store_id TEXT NOT NULL,
+                    file_id TEXT NOT NULL,
+                    chunk_index INTEGER NOT NULL,
+                    content TEXT NOT NULL,
+                    token_count INTEGER NOT NULL,
+                    char_start INTEGER NOT NULL,
+                    char_end INTEGER NOT NULL,
+                    embedding BLOB NOT NULL,
+                    metadata TEXT,
+                    created_at INTEGER NOT NULL,
+                    processing_version TEXT NOT NULL,
+                    UNIQUE(vector_store_id, file_id, chunk_index, processing_version)
+                )
+                "#
+            ),
+            format!(
+                "CREATE INDEX IF NOT EXISTS {chunks}_vector_store_idx ON {chunks} (vector_store_id)"
+            ),
+            format!("CREATE INDEX IF NOT EXISTS {chunks}_file_idx ON {chunks} (file_id)"),
+            format!(
+                "CREATE INDEX IF NOT EXISTS {chunks}_file_collection_version_idx \
+                 ON {chunks} (file_id, vector_store_id, processing_version)"
+            ),
+            // Porter stemming approximates pgvector's 'english' text search config
+            format!(
+                "CREATE VIRTUAL TABLE IF NOT EXISTS {fts} USING fts5(\
+                 content, content='{chunks}', content_rowid='seq', tokenize='porter unicode61')"
+            ),
+            format!(
+                r#"
+                CREATE TRIGGER IF NOT EXISTS {chunks}_fts_insert AFTER INSERT ON {chunks} BEGIN
+                    INSERT INTO {fts}(rowid, content) VALUES (new.seq, new.content);
+                END
+                "#
+            ),
+            format!(
+                r#"
+                CREATE TRIGGER IF NOT EXISTS {chunks}_fts_delete AFTER DELETE ON {chunks} BEGIN
+                    INSERT INTO {fts}({fts}, rowid, content) VALUES ('delete', old.seq, old.content);
+                END
+                "#
+            ),
+            format!(
+                r#"
+                CREATE TRIGGER IF NOT EXISTS {chunks}_fts_update AFTER UPDATE OF content ON {chunks} BEGIN
+                    INSERT INTO {fts}({fts}, rowid, content) VALUES ('delete', old.seq, old.content);
+                    INSERT INTO {fts}(rowid, content) VALUES (new.seq, new.content);
+                END
+                "#
+            ),
+        ];
+
+        for statement in &statements {
+            if let Err(e) = sqlx::query(statement).execute(&self.pool).await {
+                warn!(
+                    stage = "vector_operation_completed",
+                    backend = BACKEND,
+                    operation = "initialize",
+                    status = "error",
+                    error = %e,
+                    "SQLite vector store initialization failed"
+                );
+                return Err(VectorStoreError::Database(e.to_string()));
+            }
+        }
+
+        info!(
+            stage = "vector_operation_completed",
+            backend = BACKEND,
+            operation = "initialize",
+            status = "success",
+            duration_ms = start.elapsed().as_millis() as u64,
+            "SQLite vector store initialized"
+        );
+        Ok(())
+    }
+
+    /// Score a stored embedding against the query as a similarity in the same
+    /// ranges pgvector reports, so thresholds are portable between backends.
+    fn similarity(&self, query: &[f32], stored: &[f32]) -> f64 {
+        match self.distance_metric {
+            DistanceMetric::Cosine => {
+                let (mut dot, mut query_norm, mut stored_norm) = (0.0f64, 0.0f64, 0.0f64);
+                for (a, b) in query.iter().zip(stored) {
+                    let (a, b) = (*a as f64, *b as f64);
+                    dot += a * b;
+                    query_norm += a * a;
+                    stored_norm += b * b;
+                }
+                if query_norm == 0.0 || stored_norm == 0.0 {
+                    return 0.0;
+                }
+                dot / (query_norm.sqrt() * stored_norm.sqrt())
+            }
+            DistanceMetric::DotProduct => {
+                let dot: f64 = query
+                    .iter()
+                    .zip(stored)
+                    .map(|(a, b)| *a as f64 * *b as f64)
+                    .sum();
+                (1.0 + dot) / 2.0
+            }
+            DistanceMetric::Euclidean => {
+                let distance: f64 = query
+                    .iter()
+                    .zip(stored)
+                    .map(|(a, b)| {
+                        let d = *a as f64 - *b as f64;
+                        d * d
+                    })
+                    .sum::<f64>()
+                    .sqrt();
+                1.0 / (1.0 + distance)
+            }
+        }
+    }
+
+    fn check_dimensions(&self, operation: &'static str, actual: usize) -> VectorStoreResult<()> {
+        if actual == self.dimensions {
+            return Ok(());
+        }
+        warn!(
+            stage = "vector_operation_completed",
+            backend = BACKEND,
+            operation = operation,
+            status = "error",
+            error = "dimension_mismatch",
+            expected = self.dimensions,
+            actual = actual,
+            "Vector dimension mismatch"
+        );
+        otel_span_error!("Dimension mismatch");
+        Err(VectorStoreError::DimensionMismatch {
+            expected: self.dimensions,
+            actual,
+        })
+    }
+}
+
+/// Record metrics and the completion log line for an operation.
+fn finish<T>(
+    operation: &'static str,
+    metric_operation: &'static str,
+    start: Instant,
+    result: Result<T, sqlx::Error>,
+    item_count: impl FnOnce(&T) -> usize,
+) -> VectorStoreResult<T> {
+    let duration = start.elapsed().as_secs_f64();
+    let duration_ms = (duration * 1000.0) as u64;
+    match result {
+        Ok(value) => {
+            let count = item_count(&value);
+            record_vector_store_operation(
+                BACKEND,
+                metric_operation,
+                "success",
+                duration,
+                count as u32,
+            );
+            info!(
+                stage = "vector_operation_completed",
+                backend = BACKEND,
+                operation = operation,
+                status = "success",
+                duration_ms = duration_ms,
+                item_count = count,
+                "Vector operation completed"
+            );
+            otel_span_ok!();
+            Ok(value)
+        }
+        Err(e) => {
+            record_vector_store_operation(BACKEND, metric_operation, "error", duration, 0);
+            warn!(
+                stage = "vector_operation_completed",
+                backend = BACKEND,
+                operation = operation,
+                status = "error",
+                duration_ms = duration_ms,
+                error = %e,
+                "Vector operation failed"
+            );
+            otel_span_error!("{} failed: {}", operation, e);
+            Err(VectorStoreError::Database(e.to_string()))
+        }
+    }
+}
+
+fn now_secs() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+/// Encode an embedding as little-endian `f32` bytes.
+fn encode_embedding(embedding: &[f64]) -> Vec<u8> {
+    embedding
+        .iter()
+        .flat_map(|v| (*v as f32).to_le_bytes())
+        .collect()
+}
+
+/// Decode an embedding stored by [`encode_embedding`].
+fn decode_embedding(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
+
+fn parse_uuid(value: &str) -> VectorStoreResult<Uuid> {
+    Uuid::parse_str(value)
+        .map_err(|e| VectorStoreError::Database(format!("invalid UUID '{}': {}", value, e)))
+}
+
+fn placeholders(count: usize) -> String {
+    vec!["?"; count].join(", ")
+}
+
+/// Keep the best `limit` scored items. Items are only sorted once the buffer
+/// doubles, so large scans stay roughly linear.
+fn push_top_k<T>(results: &mut Vec<(f64, T)>, score: f64, item: T, limit: usize) {
+    results.push((score, item));
+    if results.len() >= limit.saturating_mul(2).max(64) {
+        truncate_top_k(results, limit);
+    }
+}
+
+fn truncate_top_k<T>(results: &mut Vec<(f64, T)>, limit: usize) {
+    results.sort_by(|a, b| b.0.total_cmp(&a.0));
+    results.truncate(limit);
+}
+
+/// Convert a free-text query into an FTS5 expression. Every term is quoted so
+/// user input can never be parsed as FTS5 syntax; terms are implicitly ANDed.
+fn fts_match_query(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| format!("\"{}\"", t))
+        .collect();
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
+// ============================================================================
+// Attribute Filter SQL Generation
+// ============================================================================
+
+/// A bind value for an attribute filter clause. SQLite binds positionally,
+/// so values are pushed in the order their `?` placeholders appear.
+#[derive(Debug, Clone, PartialEq)]
+enum FilterBind {
+    Text(String),
+    Real(f64),
+    Integer(i64),
+}
+
+/// Build a SQL WHERE fragment from an AttributeFilter for JSON metadata.
+///
+/// Semantics match pgvector: strings compare as text, numbers as REAL and
+/// booleans as JSON booleans; array values never match and an empty compound
+/// filter matches everything.
+fn build_attribute_filter_sql(filter: &AttributeFilter, binds: &mut Vec<FilterBind>) -> String {
+    match filter {
+        AttributeFilter::Comparison(comp) => build_comparison_clause(comp, binds),
+        AttributeFilter::Compound(compound) => build_compound_clause(compound, binds),
+    }
+}
+
+fn build_comparison_clause(comp: &ComparisonFilter, binds: &mut Vec<FilterBind>) -> String {
+    let op = match comp.operator {
+        ComparisonOperator::Eq => "=",
+        ComparisonOperator::Ne => "!=",
+        ComparisonOperator::Gt => ">",
+        ComparisonOperator::Gte => ">=",
+        ComparisonOperator::Lt => "<",
+        ComparisonOperator::Lte => "<=",
+    };
+    // Quoting the key keeps dots and other path characters literal
+    let path = FilterBind::Text(format!("$.\"{}\"", comp.key));
+
+    match &comp.value {
+        FilterValue::String(s) => {
+            binds.push(path);
+            binds.push(FilterBind::Text(s.clone()));
+            format!("(CAST(json_extract(metadata, ?) AS TEXT) {} ?)", op)
+        }
+        FilterValue::Number(n) => {
+            binds.push(path);
+            binds.push(FilterBind::Real(*n));
+            format!("(CAST(json_extract(metadata, ?) AS REAL) {} ?)", op)
+        }
+        FilterValue::Boolean(b) => {
+            // json_extract returns JSON true/false as 1/0
+            binds.push(path);
+            binds.push(FilterBind::Integer(i64::from(*b)));
+            format!("(json_extract(metadata, ?) {} ?)", op)
+        }
+        FilterValue::Array(_) => "FALSE".to_string(),
+    }
+}
+
+fn build_compound_clause(compound: &CompoundFilter, binds: &mut Vec<FilterBind>) -> String {
+    if compound.filters.is_empty() {
+        return "TRUE".to_string();
+    }
+
+    let logical_op = match compound.operator {
+        LogicalOperator::And => " AND ",
+        LogicalOperator::Or => " OR ",
+    };
+    let clauses: Vec<String> = compound
+        .filters
+        .iter()
+        .map(|f| build_attribute_filter_sql(f, binds))
+        .collect();
+
+    format!("({})", clauses.join(logical_op))
+}
+
+/// SQL fragments and binds shared by vector and keyword chunk searches.
+struct ChunkScope {
+    clause: String,
+    binds: Vec<FilterBind>,
+}
+
+impl ChunkScope {
+    fn new(vector_store_ids: &[Uuid], filter: Option<&ChunkFilter>) -> Self {
+        let mut binds: Vec<FilterBind> = vector_store_ids
+            .iter()
+            .map(|id| FilterBind::Text(id.to_string()))
+            .collect();
+        let mut clause = format!(
+            "c.vector_store_id IN ({})",
+            placeholders(vector_store_ids.len())
+        );
+
+        if let Some(ids) = filter.and_then(|f| f.file_ids.as_ref())
+            && !ids.is_empty()
+        {
+            clause.push_str(&format!(" AND c.file_id IN ({})", placeholders(ids.len())));
+            binds.extend(ids.iter().map(|id| FilterBind::Text(id.to_string())));
+        }
+
+        if let Some(attr_filter) = filter.and_then(|f| f.attribute_filter.as_ref()) {
+            let attr_clause = build_attribute_filter_sql(attr_filter, &mut binds);
+            clause.push_str(&format!(" AND {}", attr_clause));
+        }
+
+        Self { clause, binds }
+    }
+
+    fn bind<'q, O>(
+        &'q self,
+        mut query: sqlx::query::QueryAs<'q, sqlx::Sqlite, O, sqlx::sqlite::SqliteArguments<'q>>,
+    ) -> sqlx::query::QueryAs<'q, sqlx::Sqlite, O, sqlx::sqlite::SqliteArguments<'q>> {
+        for bind in &self.binds {
+            query = match bind {
+                FilterBind::Text(s) => query.bind(s.as_str()),
+                FilterBind::Real(n) => query.bind(*n),
+                FilterBind::Integer(i) => query.bind(*i),
+            };
+        }
+        query
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct ChunkRow {
+    id: String,
+    vector_store_id: String,
+    file_id: String,
+    chunk_index: i32,
+    content: String,
+    char_start: i32,
+    char_end: i32,
+    metadata: Option<String>,
+}
+
+impl ChunkRow {
+    fn into_result(self, score: f64) -> VectorStoreResult<ChunkSearchResult> {
+        Ok(ChunkSearchResult {
+            chunk_id: parse_uuid(&self.id)?,
+            vector_store_id: parse_uuid(&self.vector_store_id)?,
+            file_id: parse_uuid(&self.file_id)?,
+            chunk_index: self.chunk_index,
+            content: self.content,
+            char_start: self.char_start,
+            char_end: self.char_end,
+            score,
+            metadata: self.metadata.and_then(|s| serde_json::from_str(&s).ok()),
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct MetadataRow {
+    cache_key: String,
+    model: String,
+    organization_id: Option<String>,
+    project_id: Option<String>,
+    created_at: i64,
+    ttl_secs: i64,
+}
+
+impl From<MetadataRow> for VectorMetadata {
+    fn from(row: MetadataRow) -> Self {
+        Self {
+            cache_key: row.cache_key,
+            model: row.model,
+            organization_id: row.organization_id,
+            project_id: row.project_id,
+            created_at: row.created_at,
+            ttl_secs: row.ttl_secs as u64,
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl VectorBackend for SqliteVectorStore {
+    #[instrument(
+        skip(self, embedding, metadata),
+        fields(backend = "sqlite", operation = "store")
+    )]
+    async fn store(
+        &self,
+        id: &str,
+        embedding: &[f64],
+        metadata: VectorMetadata,
+        ttl: Duration,
+    ) -> VectorStoreResult<()> {
+        self.check_dimensions("store", embedding.len())?;
+
+        let start = Instant::now();
+        debug!(
+            stage = "vector_operation_started",
+            backend = BACKEND,
+            operation = "store",
+            id = %id,
+            model = %metadata.model,
+            "Starting vector store operation"
+        );
+
+        let expires_at = now_secs() + ttl.as_secs() as i64;
+        let query = format!(
+            r#"
+            INSERT INTO {} (id, embedding, cache_key, model, organization_id, project_id, created_at, ttl_secs, expires_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT (id) DO UPDATE SET
+                embedding = excluded.embedding,
+                cache_key = excluded.cache_key,
+                model = excluded.model,
+                organization_id = excluded.organization_id,
+                project_id = excluded.project_id,
+                created_at = excluded.created_at,
+                ttl_secs = excluded.ttl_secs,
+                expires_at = excluded.expires_at
+            "#,
+            self.table_name
+        );
+
+        let result = sqlx::query(&query)
+            .bind(id)
+            .bind(encode_embedding(embedding))
+            .bind(&metadata.cache_key)
+            .bind(&metadata.model)
+            .bind(&metadata.organization_id)
+            .bind(&metadata.project_id)
+            .bind(metadata.created_at)
+            .bind(metadata.ttl_secs as i64)
+            .bind(expires_at)
+            .execute(&self.pool)
+            .await;
+
+        finish("store", "upsert", start, result, |_| 1).map(|_| ())
+    }
+
+    #[instrument(skip(self, embedding), fields(backend = "sqlite", operation = "search", limit = limit))]
+    async fn search(
+        &self,
+        embedding: &[f64],
+        limit: usize,
+        threshold: f64,
+        model_filter: Option<&str>,
+        tenant_filter: VectorTenantFilter<'_>,
+    ) -> VectorStoreResult<Vec<VectorSearchResult>> {
+        self.check_dimensions("search", embedding.len())?;
+
+        let start = Instant::now();
+        debug!(
+            stage = "vector_operation_started",
+            backend = BACKEND,
+            operation = "search",
+            limit = limit,
+            threshold = threshold,
+            model_filter = ?model_filter,
+            "Starting vector search operation"
+        );
+
+        let mut where_clauses = vec!["expires_at > ?"];
+        if model_filter.is_some() {
+            where_clauses.push("model = ?");
+        }
+        where_clauses.push(match tenant_filter.organization_id {
+            Some(_) => "organization_id = ?",
+            None => "organization_id IS NULL",
+        });
+        where_clauses.push(match tenant_filter.project_id {
+            Some(_) => "project_id = ?",
+            None => "project_id IS NULL",
+        });
+
+        let query = format!(
+            r#"
+            SELECT cache_key, model, organization_id, project_id, created_at, ttl_secs, embedding
+            FROM {}
+            WHERE {}
+            "#,
+            self.table_name,
+            where_clauses.join(" AND ")
+        );
+
+        #[derive(sqlx::FromRow)]
+        struct SearchRow {
+            #[sqlx(flatten)]
+            metadata: MetadataRow,
+            embedding: Vec<u8>,
+        }
+
+        let mut q = sqlx::query_as::<_, SearchRow>(&query).bind(now_secs());
+        if let Some(model) = model_filter {
+            q = q.bind(model);
+        }
+        if let Some(org) = tenant_filter.organization_id {
+            q = q.bind(org);
+        }
+        if let Some(project) = tenant_filter.project_id {
+            q = q.bind(project);
+        }
+
+        let query_embedding: Vec<f32> = embedding.iter().map(|v| *v as f32).collect();
+        let mut scored = Vec::new();
+        let mut rows = q.fetch(&self.pool);
+        let result = loop {
+            match rows.try_next().await {
+                Ok(Some(row)) => {
+                    let stored = decode_embedding(&row.embedding);
+                    if stored.len() != self.dimensions {
+                        continue;
+                    }
+                    let similarity = self.similarity(&query_embedding, &stored);
+                    if similarity >= threshold {
+                        push_top_k(&mut scored, similarity, row.metadata, limit);
+                    }
+                }
+                Ok(None) => break Ok(()),
+                Err(e) => break Err(e),
+            }
+        };
+
+        drop(rows);
+
+        finish("search", "search", start, result, |_| {
+            scored.len().min(limit)
+        })?;
+        truncate_top_k(&mut scored, limit);
+        Ok(scored
+            .into_iter()
+            .map(|(similarity, metadata)| VectorSearchResult {
+                metadata: metadata.into(),
+                similarity,
+            })
+            .collect())
+    }
+
+    #[instrument(skip(self), fields(backend = "sqlite", operation = "delete"))]
+    async fn delete(&self, id: &str) -> VectorStoreResult<()> {
+        let start = Instant::now();
+        let query = format!("DELETE FROM {} WHERE id = ?", self.table_name);
+        let result = sqlx::query(&query).bind(id).execute(&self.pool).await;
+
+        finish("delete", "delete", start, result, |r| {
+            r.rows_affected() as usize
+        })
+        .map(|_| ())
+    }
+
+    #[instrument(skip(self), fields(backend = "sqlite", operation = "cleanup_expired"))]
+    async fn cleanup_expired(&self) -> VectorStoreResult<usize> {
+        let start = Instant::now();
+        let query = format!("DELETE FROM {} WHERE expires_at <= ?", self.table_name);
+        let result = sqlx::query(&query)
+            .bind(now_secs())
+            .execute(&self.pool)
+            .await
+            .map(|r| r.rows_affected() as usize);
+
+        finish("cleanup_expired", "cleanup", start, result, |count| *count)
+    }
+
+    #[instrument(
+        skip(self),
+        fields(backend = "sqlite", operation = "list_by_organization")
+    )]
+    async fn list_by_organization(
+        &self,
+        organization_id: &str,
+        limit: usize,
+    ) -> VectorStoreResult<Vec<VectorMetadata>> {
+        let start = Instant::now();
+        let query = format!(
+            r#"
+            SELECT cache_key, model, organization_id, project_id, created_at, ttl_secs
+            FROM {}
+            WHERE organization_id = ? AND expires_at > ?
+            ORDER BY created_at DESC
+            LIMIT ?
+            "#,
+            self.table_name
+        );
+        let result = sqlx::query_as::<_, MetadataRow>(&query)
+            .bind(organization_id)
+            .bind(now_secs())
+            .bind(limit as i64)
+            .fetch_all(&self.pool)
+            .await;
+
+        let rows = finish("list_by_organization", "list", start, result, Vec::len)?;
+        Ok(rows.into_iter().map(VectorMetadata::from).collect())
+    }
+
+    #[instrument(
+        skip(self),
+        fields(backend = "sqlite", operation = "count_by_organization")
+    )]
+    async fn count_by_organization(&self, organization_id: &str) -> VectorStoreResult<u64> {
+        let query = format!(
+            "SELECT COUNT(*) FROM {} WHERE organization_id = ? AND expires_at > ?",
+            self.table_name
+        );
+        let count: i64 = sqlx::query_scalar(&query)
+            .bind(organization_id)
+            .bind(now_secs())
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| VectorStoreError::Database(e.to_string()))?;
+        Ok(count as u64)
+    }
+
+    #[instrument(
+        skip(self),
+        fields(backend = "sqlite", operation = "delete_by_organization")
+    )]
+    async fn delete_by_organization(
+        &self,
+        organization_id: &str,
+    ) -> VectorStoreResult<Vec<String>> {
+        let start = Instant::now();
+        let query = format!(
+            "DELETE FROM {} WHERE organization_id = ? RETURNING cache_key",
+            self.table_name
+        );
+        let result = sqlx::query_scalar::<_, String>(&query)
+            .bind(organization_id)
+            .fetch_all(&self.pool)
+            .await;
+
+        finish("delete_by_organization", "delete", start, result, Vec::len)
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    #[instrument(skip(self), fields(backend = "sqlite", operation = "health_check"))]
+    async fn health_check(&self) -> VectorStoreResult<()> {
+        let query = format!("SELECT 1 FROM {} LIMIT 1", self.table_name);
+        sqlx::query(&query)
+            .execute(&self.pool)
+            .await
+            .map(|_| ())
+            .map_err(|e| VectorStoreError::Unavailable(e.to_string()))
+    }
+
+    // ========================================================================
+    // RAG VectorStore Chunk Operations
+    // ========================================================================
+
+    #[instrument(
+        skip(self, chunks),
+        fields(backend = "sqlite", operation = "store_chunks")
+    )]
+    async fn store_chunks(&self, chunks: Vec<ChunkWithEmbedding>) -> VectorStoreResult<()> {
+        if chunks.is_empty() {
+            return Ok(());
+        }
+        for chunk in &chunks {
+            self.check_dimensions("store_chunks", chunk.embedding.len())?;
+        }
+
+        let start = Instant::now();
+        let chunk_count = chunks.len();
+        debug!(
+            stage = "vector_operation_started",
+            backend = BACKEND,
+            operation = "store_chunks",
+            vector_store_id = ?chunks.first().map(|c| c.vector_store_id),
+            chunk_count = chunk_count,
+            "Starting chunk store operation"
+        );
+
+        let now = now_secs();
+        // With shadow-copy processing each processing_version creates new
+        // rows; old versions are deleted once the new version is complete.
+        let query = format!(
+            r#"
+            INSERT INTO {} (
+                id, vector_store_id, file_id, chunk_index, content,
+                token_count, char_start, char_end, embedding, metadata, created_at,
+                processing_version
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT (vector_store_id, file_id, chunk_index, processing_version) DO UPDATE SET
+                id = excluded.id,
+                content = excluded.content,
+                token_count = excluded.token_count,
+                char_start = excluded.char_start,
+                char_end = excluded.char_end,
+                embedding = excluded.embedding,
+                metadata = excluded.metadata,
+                created_at = excluded.created_at
+            "#,
+            self.chunks_table_name
+        );
+
+        let result = async {
+            let mut tx = self.pool.begin().await?;
+            for chunk in chunks {
+                let metadata_json = chunk
+                    .metadata
+                    .map(|m| serde_json::to_string(&m).unwrap_or_default());
+                sqlx::query(&query)
+                    .bind(chunk.id.to_string())
+                    .bind(chunk.vector_store_id.to_string())
+                    .bind(chunk.file_id.to_string())
+                    .bind(chunk.chunk_index)
+                    .bind(&chunk.content)
+                    .bind(chunk.token_count)
+                    .bind(chunk.char_start)
+                    .bind(chunk.char_end)
+                    .bind(encode_embedding(&chunk.embedding))
+                    .bind(metadata_json)
+                    .bind(now)
+                    .bind(chunk.processing_version.to_string())
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            tx.commit().await
+        }
+        .await;
+
+        finish("store_chunks", "insert", start, result, |_| chunk_count)
+    }
+
+    #[instrument(skip(self), fields(backend = "sqlite", operation = "get_chunks_by_file", file_id = %file_id))]
+    async fn get_chunks_by_file(&self, file_id: Uuid) -> VectorStoreResult<Vec<StoredChunk>> {
+        let start = Instant::now();
+
+        #[derive(sqlx::FromRow)]
+        struct StoredChunkRow {
+            id: String,
+            vector_store_id: String,
+            file_id: String,
+            chunk_index: i32,
+            content: String,
+            token_count: i32,
+            char_start: i32,
+            char_end: i32,
+            metadata: Option<String>,
+            created_at: i64,
+            processing_version: String,
+        }
+
+        let query = format!(
+            r#"
+            SELECT id, vector_store_id, file_id, chunk_index, content,
+                   token_count, char_start, char_end, metadata, created_at,
+                   processing_version
+            FROM {}
+            WHERE file_id = ?
+            ORDER BY chunk_index
+            "#,
+            self.chunks_table_name
+        );
+        let result = sqlx::query_as::<_, StoredChunkRow>(&query)
+            .bind(file_id.to_string())
+            .fetch_all(&self.pool)
+            .await;
+
+        let rows = finish("get_chunks_by_file", "get_chunks", start, result, Vec::len)?;
+        rows.into_iter()
+            .map(|row| {
+                Ok(StoredChunk {
+                    id: parse_uuid(&row.id)?,
+                    vector_store_id: parse_uuid(&row.vector_store_id)?,
+                    file_id: parse_uuid(&row.file_id)?,
+                    chunk_index: row.chunk_index,
+                    content: row.content,
+                    token_count: row.token_count,
+                    char_start: row.char_start,
+                    char_end: row.char_end,
+                    metadata: row.metadata.and_then(|s| serde_json::from_str(&s).ok()),
+                    created_at: row.created_at,
+                    processing_version: parse_uuid(&row.processing_version)?,
+                })
+            })
+            .collect()
+    }
+
+    #[instrument(skip(self), fields(backend = "sqlite", operation = "delete_chunks_by_file", file_id = %file_id))]
+    async fn delete_chunks_by_file(&self, file_id: Uuid) -> VectorStoreResult<u64> {
+        let start = Instant::now();
+        let query = format!("DELETE FROM {} WHERE file_id = ?", self.chunks_table_name);
+        let result = sqlx::query(&query)
+            .bind(file_id.to_string())
+            .execute(&self.pool)
+            .await
+            .map(|r| r.rows_affected());
+
+        finish("delete_chunks_by_file", "delete", start, result, |c| {
+            *c as usize
+        })
+    }
+
+    #[instrument(skip(self), fields(backend = "sqlite", operation = "delete_chunks_by_file_and_vector_store", file_id = %file_id, vector_store_id = %vector_store_id))]
+    async fn delete_chunks_by_file_and_vector_store(
+        &self,
+        file_id: Uuid,
+        vector_store_id: Uuid,
+    ) -> VectorStoreResult<u64> {
+        let start = Instant::now();
+        let query = format!(
+            "DELETE FROM {} WHERE file_id = ? AND vector_store_id = ?",
+            self.chunks_table_name
+        );
+        let result = sqlx::query(&query)
+            .bind(file_id.to_string())
+            .bind(vector_store_id.to_string())
+            .execute(&self.pool)
+            .await
+            .map(|r| r.rows_affected());
+
+        finish(
+            "delete_chunks_by_file_and_vector_store",
+            "delete",
+            start,
+            result,
+            |c| *c as usize,
+        )
+    }
+
+    #[instrument(skip(self), fields(backend = "sqlite", operation = "delete_chunks_by_file_and_vector_store_except_version", file_id = %file_id, vector_store_id = %vector_store_id, keep_version = %keep_version))]
+    async fn delete_chunks_by_file_and_vector_store_except_version(
+        &self,
+        file_id: Uuid,
+        vector_store_id: Uuid,
+        keep_version: Uuid,
+    ) -> VectorStoreResult<u64> {
+        let start = Instant::now();
+        let query = format!(
+            "DELETE FROM {} WHERE file_id = ? AND vector_store_id = ? AND processing_version != ?",
+            self.chunks_table_name
+        );
+        let result = sqlx::query(&query)
+            .bind(file_id.to_string())
+            .bind(vector_store_id.to_string())
+            .bind(keep_version.to_string())
+            .execute(&self.pool)
+            .await
+            .map(|r| r.rows_affected());
+
+        finish(
+            "delete_chunks_by_file_and_vector_store_except_version",
+            "delete",
+            start,
+            result,
+            |c| *c as usize,
+        )
+    }
+
+    #[instrument(skip(self), fields(backend = "sqlite", operation = "delete_chunks_by_vector_store", vector_store_id = %vector_store_id))]
+    async fn delete_chunks_by_vector_store(&self, vector_store_id: Uuid) -> VectorStoreResult<u64> {
+        let start = Instant::now();
+        let query = format!(
+            "DELETE FROM {} WHERE vector_store_id = ?",
+            self.chunks_table_name
+        );
+        let result = sqlx::query(&query)
+            .bind(vector_store_id.to_string())
+            .execute(&self.pool)
+            .await
+            .map(|r| r.rows_affected());
+
+        finish(
+            "delete_chunks_by_vector_store",
+            "delete",
+            start,
+            result,
+            |c| *c as usize,
+        )
+    }
+
+    async fn search_vector_store(
+        &self,
+        vector_store_id: Uuid,
+        embedding: &[f64],
+        limit: usize,
+        threshold: f64,
+        filter: Option<ChunkFilter>,
+    ) -> VectorStoreResult<Vec<ChunkSearchResult>> {
+        self.search_vector_stores(&[vector_store_id], embedding, limit, threshold, filter)
+            .await
+    }
+
+    #[instrument(skip(self, embedding, filter), fields(backend = "sqlite", operation = "search_vector_stores", vector_store_count = vector_store_ids.len(), limit = limit))]
+    async fn search_vector_stores(
+        &self,
+        vector_store_ids: &[Uuid],
+        embedding: &[f64],
+        limit: usize,
+        threshold: f64,
+        filter: Option<ChunkFilter>,
+    ) -> VectorStoreResult<Vec<ChunkSearchResult>> {
+        self.check_dimensions("search_vector_stores", embedding.len())?;
+        if vector_store_ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let start = Instant::now();
+        debug!(
+            stage = "vector_operation_started",
+            backend = BACKEND,
+            operation = "search_vector_stores",
+            vector_store_count = vector_store_ids.len(),
+            limit = limit,
+            threshold = threshold,
+            has_filter = filter.is_some(),
+            "Starting vector store search operation"
+        );
+
+        let scope = ChunkScope::new(vector_store_ids, filter.as_ref());
+        let query = format!(
+            r#"
+            SELECT c.id, c.vector_store_id, c.file_id, c.chunk_index, c.content,
+                   c.char_start, c.char_end, c.metadata, c.embedding
+            FROM {} c
+            WHERE {}
+            "#,
+            self.chunks_table_name, scope.clause
+        );
+
+        #[derive(sqlx::FromRow)]
+        struct SearchRow {
+            #[sqlx(flatten)]
+            chunk: ChunkRow,
+            embedding: Vec<u8>,
+        }
+
+        let query_embedding: Vec<f32> = embedding.iter().map(|v| *v as f32).collect();
+        let mut scored = Vec::new();
+        let mut rows = scope
+            .bind(sqlx::query_as::<_, SearchRow>(&query))
+            .fetch(&self.pool);
+        let result = loop {
+            match rows.try_next().await {
+                Ok(Some(row)) => {
+                    let stored = decode_embedding(&row.embedding);
+                    if stored.len() != self.dimensions {
+                        continue;
+                    }
+                    let score = self.similarity(&query_embedding, &stored);
+                    if score >= threshold {
+                        push_top_k(&mut scored, score, row.chunk, limit);
+                    }
+                }
+                Ok(None) => break Ok(()),
+                Err(e) => break Err(e),
+            }
+        };
+        drop(rows);
+
+        finish("search_vector_stores", "search", start, result, |_| {
+            scored.len().min(limit)
+        })?;
+        truncate_top_k(&mut scored, limit);
+        scored
+            .into_iter()
+            .map(|(score, row)| row.into_result(score))
+            .collect()
+    }
+
+    // ========================================================================
+    // Keyword Search Operations (for Hybrid Search)
+    // ========================================================================
+
+    async fn keyword_search_vector_store(
+        &self,
+        vector_store_id: Uuid,
+        query: &str,
+        limit: usize,
+        filter: Option<ChunkFilter>,
+    ) -> VectorStoreResult<Vec<ChunkSearchResult>> {
+        self.keyword_search_vector_stores(&[vector_store_id], query, limit, filter)
+            .await
+    }
+
+    #[instrument(skip(self, filter), fields(backend = "sqlite", operation = "keyword_search_vector_stores", vector_store_count = vector_store_ids.len(), limit = limit))]
+    async fn keyword_search_vector_stores(
+        &self,
+        vector_store_ids: &[Uuid],
+        query: &str,
+        limit: usize,
+        filter: Option<ChunkFilter>,
+    ) -> VectorStoreResult<Vec<ChunkSearchResult>> {
+        if vector_store_ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let Some(match_query) = fts_match_query(query) else {
+            return Ok(vec![]);
+        };
+
+        let start = Instant::now();
+        let scope = ChunkScope::new(vector_store_ids, filter.as_ref());
+        // bm25() is negative with lower meaning better; negate it so the rank is in
+        // [0, ∞) and normalize the same way as pgvector's ts_rank_cd.
+        let sql_query = format!(
+            r#"
+            SELECT c.id, c.vector_store_id, c.file_id, c.chunk_index, c.content,
+                   c.char_start, c.char_end, c.metadata, -bm25({fts}) AS bm25_rank
+            FROM {fts}
+            JOIN {chunks} c ON c.seq = {fts}.rowid
+            WHERE {fts} MATCH ? AND {clause}
+            ORDER BY bm25_rank DESC
+            LIMIT ?
+            "#,
+            fts = self.fts_table_name,
+            chunks = self.chunks_table_name,
+            clause = scope.clause,
+        );
+
+        #[derive(sqlx::FromRow)]
+        struct KeywordSearchRow {
+            #[sqlx(flatten)]
+            chunk: ChunkRow,
+            bm25_rank: f64,
+        }
+
+        let q = sqlx::query_as::<_, KeywordSearchRow>(&sql_query).bind(&match_query);
+        let result = scope.bind(q).bind(limit as i64).fetch_all(&self.pool).await;
+
+        let rows = finish(
+            "keyword_search_vector_stores",
+            "keyword_search",
+            start,
+            result,
+            Vec::len,
+        )?;
+        rows.into_iter()
+            .map(|row| {
+                let rank = row.bm25_rank.max(0.0);
+                row.chunk.into_result(rank / (1.0 + rank))
+            })
+            .collect()
+    }
+
+    // ========================================================================
+    // Hybrid Search Operations
+    // ========================================================================
+
+    async fn hybrid_search_vector_store(
+        &self,
+        vector_store_id: Uuid,
+        query: &str,
+        embedding: &[f64],
+        limit: usize,
+        config: HybridSearchConfig,
+        filter: Option<ChunkFilter>,
+    ) -> VectorStoreResult<Vec<ChunkSearchResult>> {
+        self.hybrid_search_vector_stores(
+            &[vector_store_id],
+            query,
+            embedding,
+            limit,
+            config,
+            filter,
+        )
+        .await
+    }
+
+    #[instrument(skip(self, embedding, filter, config), fields(backend = "sqlite", operation = "hybrid_search_vector_stores", vector_store_count = vector_store_ids.len(), limit = limit))]
+    async fn hybrid_search_vector_stores(
+        &self,
+        vector_store_ids: &[Uuid],
+        query: &str,
+        embedding: &[f64],
+        limit: usize,
+        config: HybridSearchConfig,
+        filter: Option<ChunkFilter>,
+    ) -> VectorStoreResult<Vec<ChunkSearchResult>> {
+        if vector_store_ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let start = Instant::now();
+        // Over-fetch from each source so RRF has overlapping candidates
+        let search_limit = limit * 3;
+        let (vector_results, keyword_results) = tokio::join!(
+            self.search_vector_stores(
+                vector_store_ids,
+                embedding,
+                search_limit,
+                config.vector_threshold,
+                filter.clone(),
+            ),
+            self.keyword_search_vector_stores(vector_store_ids, query, search_limit, filter),
+        );
+        let vector_results = vector_results?;
+        let keyword_results = keyword_results?;
+
+        let fused_results =
+            fuse_results_limited(&vector_results, &keyword_results, &config.rrf, limit);
+
+        let duration = start.elapsed().as_secs_f64();
+        record_vector_store_operation(
+            BACKEND,
+            "hybrid_search",
+            "success",
+            duration,
+            fused_results.len() as u32,
+        );
+        info!(
+            stage = "vector_operation_completed",
+            backend = BACKEND,
+            operation = "hybrid_search_vector_stores",
+            status = "success",
+            duration_ms = (duration * 1000.0) as u64,
+            item_count = fused_results.len(),
+            vector_count = vector_results.len(),
+            keyword_count = keyword_results.len(),
+            "Hybrid search operation completed"
+        );
+        otel_span_ok!();
+        Ok(fused_results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedding_roundtrip() {
+        let embedding = vec![0.25, -1.5, 3.0];
+        let bytes = encode_embedding(&embedding);
+        assert_eq!(bytes.len(), 12);
+        assert_eq!(decode_embedding(&bytes), vec![0.25f32, -1.5, 3.0]);
+    }
+
+    #[test]
+    fn test_fts_match_query_quotes_terms() {
+        assert_eq!(
+            fts_match_query("XJ-900 part").as_deref(),
+            Some("\"XJ\" \"900\" \"part\"")
+        );
+        // FTS5 operators are treated as plain terms
+        assert_eq!(
+            fts_match_query("cats OR NOT dogs*").as_deref(),
+            Some("\"cats\" \"OR\" \"NOT\" \"dogs\"")
+        );
+        assert_eq!(fts_match_query("   "), None);
+        assert_eq!(fts_match_query("--"), None);
+    }
+
+    #[test]
+    fn test_top_k_keeps_best_scores() {
+        let mut results = Vec::new();
+        for i in 0..200 {
+            push_top_k(&mut results, i as f64, i, 3);
+        }
+        truncate_top_k(&mut results, 3);
+        let kept: Vec<i32> = results.into_iter().map(|(_, i)| i).collect();
+        assert_eq!(kept, vec![199, 198, 197]);
+    }
+
+    #[test]
+    fn test_build_attribute_filter_sql_nested_compound() {
+        let filter = AttributeFilter::and(vec![
+            AttributeFilter::eq("category", "documentation"),
+            AttributeFilter::or(vec![
+                AttributeFilter::gte("year", 2024),
+                AttributeFilter::eq("published", true),
+            ]),
+        ]);
+        let mut binds = Vec::new();
+        let clause = build_attribute_filter_sql(&filter, &mut binds);
+
+        assert_eq!(
+            clause,
+            "((CAST(json_extract(metadata, ?) AS TEXT) = ?) AND \
+             ((CAST(json_extract(metadata, ?) AS REAL) >= ?) OR (json_extract(metadata, ?) = ?)))"
+        );
+        assert_eq!(
+            binds,
+            vec![
+                FilterBind::Text("$.\"category\"".into()),
+                FilterBind::Text("documentation".into()),
+                FilterBind::Text("$.\"year\"".into()),
+                FilterBind::Real(2024.0),
+                FilterBind::Text("$.\"published\"".into()),
+                FilterBind::Integer(1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_attribute_filter_sql_edge_cases() {
+        let mut binds = Vec::new();
+        assert_eq!(
+            build_attribute_filter_sql(&AttributeFilter::and(vec![]), &mut binds),
+            "TRUE"
+        );
+        assert!(binds.is_empty());
+    }
+}
@@ -75,6 +75,25 @@ impl VectorBackend for TestVectorStore {
         Ok(())
     }
 
+    async fn list_by_organization(
+        &self,
+        _organization_id: &str,
+        _limit: usize,
+    ) -> VectorStoreResult<Vec<VectorMetadata>> {
+        Ok(vec![])
+    }
+
+    async fn count_by_organization(&self, _organization_id: &str) -> VectorStoreResult<u64> {
+        Ok(0)
+    }
+
+    async fn delete_by_organization(
+        &self,
+        _organization_id: &str,
+    ) -> VectorStoreResult<Vec<String>> {
+        Ok(vec![])
+    }
+
     // RAG VectorStore Chunk Operations
 
     async fn store_chunks(&self, _chunks: Vec<ChunkWithEmbedding>) -> VectorStoreResult<()> {
@@ -197,6 +216,8 @@ impl VectorBackend for TestVectorStore {
 ///         file_id: file_uuid,
 ///         chunk_index: 0,
 ///         content: "Test content".to_string(),
+///         char_start: 0,
+///         char_end: 0,
 ///         score: 0.95,
 ///         metadata: None,
 ///     },
@@ -270,6 +291,25 @@ impl VectorBackend for MockableTestVectorStore {
         Ok(())
     }
 
+    async fn list_by_organization(
+        &self,
+        _organization_id: &str,
+        _limit: usize,
+    ) -> VectorStoreResult<Vec<VectorMetadata>> {
+        Ok(vec![])
+    }
+
+    async fn count_by_organization(&self, _organization_id: &str) -> VectorStoreResult<u64> {
+        Ok(0)
+    }
+
+    async fn delete_by_organization(
+        &self,
+        _organization_id: &str,
+    ) -> VectorStoreResult<Vec<String>> {
+        Ok(vec![])
+    }
+
     async fn store_chunks(&self, _chunks: Vec<ChunkWithEmbedding>) -> VectorStoreResult<()> {
         Ok(())
     }
@@ -446,6 +486,8 @@ mod tests {
             file_id,
             chunk_index: 0,
             content: "Test content".to_string(),
+            char_start: 0,
+            char_end: 0,
             score: 0.95,
             metadata: None,
         }];
@@ -474,6 +516,8 @@ mod tests {
                 file_id,
                 chunk_index: i,
                 content: format!("Content {}", i),
+                char_start: 0,
+                char_end: 0,
                 score: 0.9 - (i as f64 * 0.1),
                 metadata: None,
             })
@@ -510,6 +554,8 @@ mod tests {
             file_id: Uuid::new_v4(),
             chunk_index: 0,
             content: "Late configured".to_string(),
+            char_start: 0,
+            char_end: 0,
             score: 0.8,
             metadata: None,
         }];
@@ -2203,3 +2203,295 @@ pub mod qdrant {
         test_hybrid_search_rrf_score(&store).await;
     }
 }
+
+// ============================================================================
+// SQLite Tests
+// ============================================================================
+
+#[cfg(all(test, feature = "database-sqlite"))]
+pub mod sqlite {
+    use super::*;
+    use crate::{cache::vector_store::SqliteVectorStore, config::DistanceMetric};
+
+    const TEST_DIMENSIONS: usize = 128; // Small for faster tests
+
+    /// Create a store backed by a fresh in-memory database
+    async fn create_test_store() -> SqliteVectorStore {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create SQLite pool");
+
+        let store = SqliteVectorStore::new(
+            pool,
+            "embeddings".to_string(),
+            TEST_DIMENSIONS,
+            DistanceMetric::default(),
+        );
+        store
+            .initialize()
+            .await
+            .expect("Failed to initialize SQLite vector store");
+        store
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_store_and_search() {
+        let store = create_test_store().await;
+        test_store_and_search(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_similar_embedding() {
+        let store = create_test_store().await;
+        test_search_with_similar_embedding(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_threshold_filtering() {
+        let store = create_test_store().await;
+        test_search_threshold_filtering(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_model_filter() {
+        let store = create_test_store().await;
+        test_model_filter(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_delete() {
+        let store = create_test_store().await;
+        test_delete(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_dimension_mismatch() {
+        let store = create_test_store().await;
+        test_dimension_mismatch(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_cleanup_expired() {
+        let store = create_test_store().await;
+        test_cleanup_expired(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_health_check() {
+        let store = create_test_store().await;
+        test_health_check(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_upsert() {
+        let store = create_test_store().await;
+        test_upsert(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_store_and_search_chunks() {
+        let store = create_test_store().await;
+        test_store_and_search_chunks(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_get_chunks_by_file() {
+        let store = create_test_store().await;
+        test_get_chunks_by_file(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_delete_chunks_by_file() {
+        let store = create_test_store().await;
+        test_delete_chunks_by_file(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_delete_chunks_by_vector_store() {
+        let store = create_test_store().await;
+        test_delete_chunks_by_vector_store(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_delete_chunks_by_file_and_vector_store() {
+        let store = create_test_store().await;
+        test_delete_chunks_by_file_and_vector_store(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_search_vector_stores_multi() {
+        let store = create_test_store().await;
+        test_search_vector_stores_multi(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_search_with_file_filter() {
+        let store = create_test_store().await;
+        test_search_with_file_filter(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_chunk_dimension_mismatch() {
+        let store = create_test_store().await;
+        test_chunk_dimension_mismatch(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_empty_chunks() {
+        let store = create_test_store().await;
+        test_empty_chunks(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_keyword_search_basic() {
+        let store = create_test_store().await;
+        test_keyword_search_basic(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_keyword_search_empty_query() {
+        let store = create_test_store().await;
+        test_keyword_search_empty_query(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_keyword_search_empty_collections() {
+        let store = create_test_store().await;
+        test_keyword_search_empty_vector_stores(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_keyword_search_multi_collection() {
+        let store = create_test_store().await;
+        test_keyword_search_multi_vector_store(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_keyword_search_with_file_filter() {
+        let store = create_test_store().await;
+        test_keyword_search_with_file_filter(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_keyword_search_score_normalization() {
+        let store = create_test_store().await;
+        test_keyword_search_score_normalization(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_hybrid_search_basic() {
+        let store = create_test_store().await;
+        test_hybrid_search_basic(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_hybrid_search_empty_results() {
+        let store = create_test_store().await;
+        test_hybrid_search_empty_results(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_hybrid_search_multi_collection() {
+        let store = create_test_store().await;
+        test_hybrid_search_multi_vector_store(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_hybrid_search_with_filter() {
+        let store = create_test_store().await;
+        test_hybrid_search_with_filter(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_hybrid_search_weighted() {
+        let store = create_test_store().await;
+        test_hybrid_search_weighted(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_hybrid_search_empty_collections() {
+        let store = create_test_store().await;
+        test_hybrid_search_empty_vector_stores(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_hybrid_search_rrf_score() {
+        let store = create_test_store().await;
+        test_hybrid_search_rrf_score(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_keyword_search_after_chunk_update() {
+        let store = create_test_store().await;
+        let vector_store_id = Uuid::new_v4();
+        let file_id = Uuid::new_v4();
+        let mut chunk =
+            create_keyword_test_chunk(TEST_DIMENSIONS, vector_store_id, file_id, 0, "alpha", 1.0);
+        store.store_chunks(vec![chunk.clone()]).await.unwrap();
+
+        // Re-storing the same chunk slot replaces its content in the FTS index
+        chunk.id = Uuid::new_v4();
+        chunk.content = "bravo".to_string();
+        store.store_chunks(vec![chunk]).await.unwrap();
+
+        let stale = store
+            .keyword_search_vector_store(vector_store_id, "alpha", 10, None)
+            .await
+            .unwrap();
+        assert!(stale.is_empty());
+        let fresh = store
+            .keyword_search_vector_store(vector_store_id, "bravo", 10, None)
+            .await
+            .unwrap();
+        assert_eq!(fresh.len(), 1);
+
+        store.delete_chunks_by_file(file_id).await.unwrap();
+        let deleted = store
+            .keyword_search_vector_store(vector_store_id, "bravo", 10, None)
+            .await
+            .unwrap();
+        assert!(deleted.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_attribute_filter() {
+        let store = create_test_store().await;
+        let vector_store_id = Uuid::new_v4();
+        let file_id = Uuid::new_v4();
+        let mut first = create_keyword_test_chunk(
+            TEST_DIMENSIONS,
+            vector_store_id,
+            file_id,
+            0,
+            "quarterly report",
+            1.0,
+        );
+        first.metadata = Some(serde_json::json!({"region": "emea", "year": 2024}));
+        let mut second = create_keyword_test_chunk(
+            TEST_DIMENSIONS,
+            vector_store_id,
+            file_id,
+            1,
+            "quarterly report",
+            1.0,
+        );
+        second.metadata = Some(serde_json::json!({"region": "apac", "year": 2023}));
+        store.store_chunks(vec![first, second]).await.unwrap();
+
+        let filter = ChunkFilter {
+            file_ids: None,
+            attribute_filter: Some(crate::models::AttributeFilter::and(vec![
+                crate::models::AttributeFilter::eq("region", "emea"),
+                crate::models::AttributeFilter::gte("year", 2024),
+            ])),
+        };
+        let results = store
+            .keyword_search_vector_store(vector_store_id, "report", 10, Some(filter))
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].chunk_index, 0);
+    }
+}
@@ -27,7 +27,8 @@ mod registry;
 mod types;
 
 pub use registry::{
-    ModelCapabilities, ModelCatalogRegistry, ModelModalities, resolve_catalog_provider_id,
+    CatalogPricing, ModelCapabilities, ModelCatalogRegistry, ModelLimits, ModelModalities,
+    resolve_catalog_provider_id,
 };
 
 /// Embedded catalog assets from the data/ directory.
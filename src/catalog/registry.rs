@@ -8,12 +8,18 @@ use std::{collections::HashMap, sync::Arc};
 use serde::{Deserialize, Serialize};
 
 use super::types::{CatalogCost, CatalogModel, ModelCatalog};
-use crate::{compat::RwLock, pricing::ModelPricing};
+use crate::{
+    compat::RwLock,
+    db::{DbPool, DbResult},
+    models::{CatalogEntryMetadata, ModelCatalogEntry},
+    pricing::ModelPricing,
+};
 
 /// Model capabilities extracted from the catalog.
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 #[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(default)]
 pub struct ModelCapabilities {
     /// Whether the model supports image/file attachments (vision)
     pub vision: bool,
@@ -29,12 +35,31 @@ pub struct ModelCapabilities {
 
     /// Whether the model supports temperature control
     pub temperature: bool,
+
+    /// Whether the model supports JSON mode (`response_format: json_object`).
+    /// Unset falls back to `structured_output`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json_mode: Option<bool>,
+
+    /// Whether the model can call several tools in one turn. Unset when
+    /// unknown.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parallel_tool_calls: Option<bool>,
+}
+
+impl ModelCapabilities {
+    /// Whether the model supports JSON mode, falling back to
+    /// `structured_output` when unknown.
+    pub fn supports_json_mode(&self) -> bool {
+        self.json_mode.unwrap_or(self.structured_output)
+    }
 }
 
 /// Model limits from the catalog.
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 #[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(default)]
 pub struct ModelLimits {
     /// Maximum context window size (tokens)
     pub context_length: Option<i64>,
@@ -47,6 +72,7 @@ pub struct ModelLimits {
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 #[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(default)]
 pub struct ModelModalities {
     /// Supported input modalities (e.g., "text", "image", "audio")
     pub input: Vec<String>,
@@ -106,15 +132,38 @@ pub struct ModelEnrichment {
     /// Model release date
     pub release_date: Option<String>,
 
+    /// Knowledge cutoff date (YYYY-MM or YYYY-MM-DD)
+    pub knowledge_cutoff: Option<String>,
+
+    /// Date the provider deprecated the model (YYYY-MM-DD)
+    pub deprecation_date: Option<String>,
+
+    /// Date the provider stops serving the model (YYYY-MM-DD)
+    pub sunset_date: Option<String>,
+
     /// Whether the model has open weights
     pub open_weights: bool,
 }
 
+/// A catalog entry added through the admin API.
+#[derive(Debug, Clone)]
+struct CustomModel {
+    enrichment: ModelEnrichment,
+    /// Whether the entry has its own pricing. Entries without it use the
+    /// pricing of the models.dev entry they override, if any.
+    priced: bool,
+}
+
 /// Thread-safe registry for model catalog data.
+///
+/// Holds two layers: models.dev data, replaced on each sync, and custom
+/// entries from the database, which take precedence.
 #[derive(Clone)]
 pub struct ModelCatalogRegistry {
     /// Map from (provider_id, model_id) to enrichment data
     inner: Arc<RwLock<HashMap<(String, String), ModelEnrichment>>>,
+    /// Custom entries by (provider_id, model_id)
+    custom: Arc<RwLock<HashMap<(String, String), CustomModel>>>,
 }
 
 impl Default for ModelCatalogRegistry {
@@ -128,12 +177,14 @@ impl ModelCatalogRegistry {
     pub fn new() -> Self {
         Self {
             inner: Arc::new(RwLock::new(HashMap::new())),
+            custom: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
     /// Load catalog data from JSON string.
     ///
-    /// This replaces all existing data in the registry.
+    /// This replaces all models.dev data in the registry; custom entries are
+    /// kept.
     pub fn load_from_json(&self, json: &str) -> Result<(), serde_json::Error> {
         let catalog: ModelCatalog = serde_json::from_str(json)?;
         self.load_from_catalog(&catalog);
@@ -155,27 +206,90 @@ impl ModelCatalogRegistry {
         *inner = data;
     }
 
+    /// Replace the custom entries, e.g. with those just loaded from the
+    /// database.
+    pub fn set_custom_entries(&self, entries: &[ModelCatalogEntry]) {
+        let custom = entries
+            .iter()
+            .map(|entry| {
+                let model = CustomModel {
+                    enrichment: Self::metadata_to_enrichment(&entry.metadata),
+                    priced: entry.metadata.pricing.is_some(),
+                };
+                ((entry.provider.clone(), entry.model.clone()), model)
+            })
+            .collect();
+        *self.custom.write() = custom;
+    }
+
+    /// Reload the custom entries from the database.
+    pub async fn load_custom_entries(&self, db: &DbPool) -> DbResult<()> {
+        let entries = db.model_catalog_entries().list().await?;
+        self.set_custom_entries(&entries);
+        Ok(())
+    }
+
     /// Look up enrichment data for a model.
     pub fn lookup(&self, provider_id: &str, model_id: &str) -> Option<ModelEnrichment> {
+        let key = (provider_id.to_string(), model_id.to_string());
         let inner = self.inner.read();
-        inner
-            .get(&(provider_id.to_string(), model_id.to_string()))
-            .cloned()
+        let Some(custom) = self.custom.read().get(&key).cloned() else {
+            return inner.get(&key).cloned();
+        };
+
+        let mut enrichment = custom.enrichment;
+        if !custom.priced
+            && let Some(base) = inner.get(&key)
+        {
+            enrichment.pricing = base.pricing.clone();
+            enrichment.catalog_pricing = base.catalog_pricing.clone();
+        }
+        Some(enrichment)
     }
 
     /// Get pricing for a model (for cost calculation).
     ///
     /// Optimized to only clone the `ModelPricing` instead of the full `ModelEnrichment`.
     pub fn get_pricing(&self, provider_id: &str, model_id: &str) -> Option<ModelPricing> {
+        let key = (provider_id.to_string(), model_id.to_string());
+        if let Some(custom) = self.custom.read().get(&key)
+            && custom.priced
+        {
+            return Some(custom.enrichment.pricing.clone());
+        }
         let inner = self.inner.read();
-        inner
-            .get(&(provider_id.to_string(), model_id.to_string()))
-            .map(|e| e.pricing.clone())
+        inner.get(&key).map(|e| e.pricing.clone())
     }
 
     /// Get the number of models in the registry.
     pub fn model_count(&self) -> usize {
-        self.inner.read().len()
+        let inner = self.inner.read();
+        let custom = self.custom.read();
+        inner.len() + custom.keys().filter(|k| !inner.contains_key(*k)).count()
+    }
+
+    /// Get the number of custom entries.
+    pub fn custom_entry_count(&self) -> usize {
+        self.custom.read().len()
+    }
+
+    /// Convert custom entry metadata to enrichment data.
+    fn metadata_to_enrichment(metadata: &CatalogEntryMetadata) -> ModelEnrichment {
+        let catalog_pricing = metadata.pricing.clone().unwrap_or_default();
+        ModelEnrichment {
+            capabilities: metadata.capabilities.clone(),
+            limits: metadata.limits.clone(),
+            pricing: catalog_pricing_to_model_pricing(&catalog_pricing),
+            catalog_pricing,
+            modalities: metadata.modalities.clone(),
+            tasks: Vec::new(),
+            family: metadata.family.clone(),
+            release_date: metadata.release_date.clone(),
+            knowledge_cutoff: metadata.knowledge_cutoff.clone(),
+            deprecation_date: metadata.deprecation_date.clone(),
+            sunset_date: metadata.sunset_date.clone(),
+            open_weights: metadata.open_weights,
+        }
     }
 
     /// Convert a catalog model to enrichment data.
@@ -187,6 +301,8 @@ impl ModelCatalogRegistry {
                 tool_call: model.tool_call,
                 structured_output: model.structured_output,
                 temperature: model.temperature,
+                json_mode: model.json_mode,
+                parallel_tool_calls: model.parallel_tool_calls,
             },
             limits: ModelLimits {
                 context_length: if model.limit.context > 0 {
@@ -215,6 +331,9 @@ impl ModelCatalogRegistry {
             tasks: Vec::new(),
             family: model.family.clone(),
             release_date: model.release_date.clone(),
+            knowledge_cutoff: model.knowledge.clone(),
+            deprecation_date: model.deprecation_date.clone(),
+            sunset_date: model.sunset_date.clone(),
             open_weights: model.open_weights,
         }
     }
@@ -246,6 +365,19 @@ pub fn catalog_cost_to_model_pricing(cost: &CatalogCost) -> ModelPricing {
     }
 }
 
+/// Convert display pricing (dollars per 1M tokens) to ModelPricing
+/// (microcents per 1M tokens).
+fn catalog_pricing_to_model_pricing(pricing: &CatalogPricing) -> ModelPricing {
+    ModelPricing {
+        input_per_1m_tokens: dollars_to_microcents(pricing.input),
+        output_per_1m_tokens: dollars_to_microcents(pricing.output),
+        reasoning_per_1m_tokens: pricing.reasoning.map(dollars_to_microcents),
+        cached_input_per_1m_tokens: pricing.cache_read.map(dollars_to_microcents),
+        cache_write_per_1m_tokens: pricing.cache_write.map(dollars_to_microcents),
+        ..Default::default()
+    }
+}
+
 /// Convert dollars to microcents (1/1,000,000 of a dollar).
 fn dollars_to_microcents(dollars: f64) -> i64 {
     (dollars * 1_000_000.0).round() as i64
@@ -338,6 +470,8 @@ mod tests {
                         "reasoning": true,
                         "tool_call": true,
                         "temperature": true,
+                        "knowledge": "2025-03",
+                        "deprecation_date": "2026-11-24",
                         "cost": {
                             "input": 5.0,
                             "output": 25.0,
@@ -369,6 +503,87 @@ mod tests {
         assert_eq!(enrichment.limits.max_output_tokens, Some(64000));
         assert_eq!(enrichment.family, Some("claude-opus".to_string()));
         assert_eq!(enrichment.modalities.input, vec!["text", "image"]);
+        assert_eq!(enrichment.knowledge_cutoff.as_deref(), Some("2025-03"));
+        assert_eq!(enrichment.deprecation_date.as_deref(), Some("2026-11-24"));
+        assert!(enrichment.sunset_date.is_none());
+        // JSON mode falls back to structured output when the catalog omits it
+        assert_eq!(enrichment.capabilities.json_mode, None);
+        assert!(!enrichment.capabilities.supports_json_mode());
+    }
+
+    #[test]
+    fn test_custom_entries_override_catalog() {
+        use chrono::Utc;
+        use uuid::Uuid;
+
+        let registry = ModelCatalogRegistry::new();
+        registry
+            .load_from_json(
+                r#"{"openai": {"id": "openai", "name": "OpenAI", "models": {
+                    "gpt-4o": {"id": "gpt-4o", "name": "GPT-4o", "attachment": true,
+                               "cost": {"input": 2.5, "output": 10.0}}
+                }}}"#,
+            )
+            .unwrap();
+
+        let entry =
+            |provider: &str, model: &str, pricing: Option<CatalogPricing>| ModelCatalogEntry {
+                id: Uuid::new_v4(),
+                provider: provider.to_string(),
+                model: model.to_string(),
+                metadata: CatalogEntryMetadata {
+                    limits: ModelLimits {
+                        context_length: Some(8192),
+                        max_output_tokens: None,
+                    },
+                    pricing,
+                    ..Default::default()
+                },
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            };
+        registry.set_custom_entries(&[
+            // Overrides gpt-4o without pricing of its own
+            entry("openai", "gpt-4o", None),
+            entry(
+                "vllm",
+                "llama-ft",
+                Some(CatalogPricing {
+                    input: 0.1,
+                    output: 0.2,
+                    ..Default::default()
+                }),
+            ),
+        ]);
+        assert_eq!(registry.model_count(), 2);
+        assert_eq!(registry.custom_entry_count(), 2);
+
+        let gpt = registry.lookup("openai", "gpt-4o").unwrap();
+        assert!(!gpt.capabilities.vision);
+        assert_eq!(gpt.limits.context_length, Some(8192));
+        assert_eq!(gpt.catalog_pricing.input, 2.5);
+        assert_eq!(
+            registry
+                .get_pricing("openai", "gpt-4o")
+                .unwrap()
+                .input_per_1m_tokens,
+            2_500_000
+        );
+        assert_eq!(
+            registry
+                .get_pricing("vllm", "llama-ft")
+                .unwrap()
+                .output_per_1m_tokens,
+            200_000
+        );
+
+        // A sync replaces models.dev data but keeps custom entries
+        registry.load_from_json("{}").unwrap();
+        assert!(registry.lookup("vllm", "llama-ft").is_some());
+        assert!(registry.get_pricing("openai", "gpt-4o").is_none());
+
+        registry.set_custom_entries(&[]);
+        assert_eq!(registry.model_count(), 0);
     }
 
     #[test]
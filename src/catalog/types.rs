@@ -69,6 +69,15 @@ pub struct CatalogModel {
     #[serde(default)]
     pub temperature: bool,
 
+    /// Whether the model supports JSON mode (`response_format: json_object`).
+    /// Catalogs without this field fall back to `structured_output`.
+    #[serde(default)]
+    pub json_mode: Option<bool>,
+
+    /// Whether the model can call several tools in one turn
+    #[serde(default)]
+    pub parallel_tool_calls: Option<bool>,
+
     /// Whether the model has open weights
     #[serde(default)]
     pub open_weights: bool,
@@ -85,6 +94,14 @@ pub struct CatalogModel {
     #[serde(default)]
     pub last_updated: Option<String>,
 
+    /// Date the provider deprecated the model (YYYY-MM-DD format)
+    #[serde(default)]
+    pub deprecation_date: Option<String>,
+
+    /// Date the provider stops serving the model (YYYY-MM-DD format)
+    #[serde(default)]
+    pub sunset_date: Option<String>,
+
     /// Input/output modalities
     #[serde(default)]
     pub modalities: CatalogModalities,
@@ -300,6 +317,27 @@ mod tests {
         assert_eq!(model.limit.output, 64000);
         assert_eq!(model.modalities.input, vec!["text", "image", "pdf"]);
         assert_eq!(model.modalities.output, vec!["text"]);
+        assert!(model.json_mode.is_none());
+        assert!(model.deprecation_date.is_none());
+    }
+
+    #[test]
+    fn test_parse_model_lifecycle_and_capability_flags() {
+        let json = r#"{
+            "id": "gpt-4-0613",
+            "name": "GPT-4 (0613)",
+            "tool_call": true,
+            "json_mode": true,
+            "parallel_tool_calls": false,
+            "deprecation_date": "2024-06-06",
+            "sunset_date": "2025-06-06"
+        }"#;
+
+        let model: CatalogModel = serde_json::from_str(json).unwrap();
+        assert_eq!(model.json_mode, Some(true));
+        assert_eq!(model.parallel_tool_calls, Some(false));
+        assert_eq!(model.deprecation_date.as_deref(), Some("2024-06-06"));
+        assert_eq!(model.sunset_date.as_deref(), Some("2025-06-06"));
     }
 
     #[test]
@@ -89,6 +89,7 @@ pub(crate) async fn run_bootstrap(explicit_config_path: Option<&str>, dry_run: b
     let services = services::Services::new(db.clone(), file_storage, max_cel, max_skill_bytes);
 
     let api_key_prefix = config.auth.api_key_config().generation_prefix();
+    let api_key_hasher = models::ApiKeyHasher::from_config(config.auth.api_key_config());
     let mut summary = Vec::new();
 
     // 1. Create org if configured
@@ -250,6 +251,7 @@ pub(crate) async fn run_bootstrap(explicit_config_path: Option<&str>, dry_run: b
                             sovereignty_requirements: None,
                         },
                         &api_key_prefix,
+                        &api_key_hasher,
                     )
                     .await
                 {
@@ -0,0 +1,276 @@
+//! `hadrian serve --check`.
+//!
+//! Performs every startup step the server would (config, TLS, database with
+//! migrations, cache, secrets, providers, router) without binding the
+//! listener, prints a report and exits 0 if the gateway would boot, 1 if not.
+//! Meant for CI/CD to gate deploys on a config that actually starts.
+
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+use super::find_config_path;
+use crate::{
+    app::{AppState, build_app},
+    config,
+    init::create_provider_instance,
+    observability,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    Ok,
+    Warn,
+    Fail,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Ok => "[ok]  ",
+            Self::Warn => "[warn]",
+            Self::Fail => "[fail]",
+        })
+    }
+}
+
+struct Check {
+    name: String,
+    outcome: Outcome,
+    detail: String,
+}
+
+#[derive(Default)]
+struct Report {
+    checks: Vec<Check>,
+}
+
+impl Report {
+    fn record(&mut self, name: impl Into<String>, outcome: Outcome, detail: impl Into<String>) {
+        self.checks.push(Check {
+            name: name.into(),
+            outcome,
+            detail: detail.into(),
+        });
+    }
+
+    fn ok(&mut self, name: impl Into<String>, detail: impl Into<String>) {
+        self.record(name, Outcome::Ok, detail);
+    }
+
+    fn warn(&mut self, name: impl Into<String>, detail: impl Into<String>) {
+        self.record(name, Outcome::Warn, detail);
+    }
+
+    fn fail(&mut self, name: impl Into<String>, detail: impl Into<String>) {
+        self.record(name, Outcome::Fail, detail);
+    }
+
+    fn count(&self, outcome: Outcome) -> usize {
+        self.checks.iter().filter(|c| c.outcome == outcome).count()
+    }
+
+    /// Print the report and exit with its status.
+    fn finish(self) -> ! {
+        let width = self.checks.iter().map(|c| c.name.len()).max().unwrap_or(0);
+        for check in &self.checks {
+            println!(
+                "  {} {:width$}  {}",
+                check.outcome, check.name, check.detail
+            );
+        }
+        let failures = self.count(Outcome::Fail);
+        let warnings = self.count(Outcome::Warn);
+        println!();
+        if failures == 0 {
+            println!("Result: OK ({warnings} warning(s))");
+            std::process::exit(0);
+        }
+        println!("Result: FAILED ({failures} failure(s), {warnings} warning(s))");
+        std::process::exit(1);
+    }
+}
+
+fn elapsed(start: Instant) -> String {
+    format!("{}ms", start.elapsed().as_millis())
+}
+
+/// Timeout for each connectivity probe, so an unreachable backend fails the
+/// check instead of hanging the pipeline.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Run the startup checks and exit.
+pub(crate) async fn run_check(explicit_config_path: Option<&str>) {
+    let mut report = Report::default();
+
+    // Unlike `serve`, never write a default config: checking one is pointless.
+    let config_path = match find_config_path(explicit_config_path) {
+        Ok(Some(path)) => path,
+        Ok(None) => {
+            report.fail(
+                "config",
+                "no config file found; pass --config or create hadrian.toml",
+            );
+            report.finish();
+        }
+        Err(e) => {
+            report.fail("config", e);
+            report.finish();
+        }
+    };
+    println!("Checking {}", config_path.display());
+    println!();
+
+    let config = match config::GatewayConfig::from_file(&config_path) {
+        Ok(c) => {
+            report.ok(
+                "config",
+                format!(
+                    "parsed and validated, {} provider(s)",
+                    c.providers.iter().count()
+                ),
+            );
+            c
+        }
+        Err(e) => {
+            report.fail("config", e.to_string());
+            report.finish();
+        }
+    };
+
+    let _tracing_guard = match observability::init_tracing(&config.observability) {
+        Ok(g) => Some(g),
+        Err(e) => {
+            report.fail(
+                "observability",
+                format!("failed to initialize tracing: {e}"),
+            );
+            None
+        }
+    };
+
+    check_tls(&config, &mut report);
+    check_security(&config, &mut report);
+
+    // AppState::new is the same initialization `serve` runs: it connects to
+    // the database and applies migrations, connects the cache, resolves
+    // secrets and builds every configured subsystem.
+    let start = Instant::now();
+    let state = match AppState::new(config.clone()).await {
+        Ok(state) => {
+            report.ok("app_state", format!("initialized in {}", elapsed(start)));
+            state
+        }
+        Err(e) => {
+            report.fail("app_state", e.to_string());
+            report.finish();
+        }
+    };
+
+    if let Some(db) = &state.db {
+        let start = Instant::now();
+        match tokio::time::timeout(PROBE_TIMEOUT, db.health_check()).await {
+            Ok(Ok(())) => report.ok("database", format!("reachable in {}", elapsed(start))),
+            Ok(Err(e)) => report.fail("database", e.to_string()),
+            Err(_) => report.fail("database", "health check timed out"),
+        }
+    } else {
+        report.warn(
+            "database",
+            "not configured; admin API and usage tracking are off",
+        );
+    }
+
+    if let Some(cache) = &state.cache {
+        let start = Instant::now();
+        match tokio::time::timeout(PROBE_TIMEOUT, cache.get_bytes("__config_check__")).await {
+            Ok(Ok(_)) => report.ok("cache", format!("reachable in {}", elapsed(start))),
+            Ok(Err(e)) => report.fail("cache", e.to_string()),
+            Err(_) => report.fail("cache", "health check timed out"),
+        }
+    }
+
+    if let Some(secrets) = &state.secrets {
+        let start = Instant::now();
+        match tokio::time::timeout(PROBE_TIMEOUT, secrets.health_check()).await {
+            Ok(Ok(())) => report.ok("secrets", format!("reachable in {}", elapsed(start))),
+            Ok(Err(e)) => report.fail("secrets", e.to_string()),
+            Err(_) => report.fail("secrets", "health check timed out"),
+        }
+    }
+
+    let mut providers: Vec<_> = config.providers.iter().collect();
+    providers.sort_by(|a, b| a.0.cmp(b.0));
+    for (name, provider_config) in providers {
+        let check = format!("provider:{name}");
+        match create_provider_instance(provider_config, name, &state.circuit_breakers) {
+            Ok(_) => report.ok(check, "created"),
+            Err(e) => report.fail(check, e),
+        }
+    }
+    if config.providers.is_empty() {
+        report.warn(
+            "providers",
+            "none configured; only dynamic providers can serve requests",
+        );
+    }
+
+    let _app = build_app(&config, state);
+    report.ok("router", "built");
+
+    report.finish();
+}
+
+#[cfg(feature = "tls")]
+fn check_tls(config: &config::GatewayConfig, report: &mut Report) {
+    if let Some(tls) = config.server.tls.as_ref() {
+        match super::tls::acceptor(tls) {
+            Ok(_) => report.ok("tls", "certificate and key loaded"),
+            Err(e) => report.fail("tls", e.to_string()),
+        }
+    }
+}
+
+#[cfg(not(feature = "tls"))]
+fn check_tls(config: &config::GatewayConfig, report: &mut Report) {
+    match config.server.tls.as_ref() {
+        Some(tls) if tls.acknowledge_unsupported => report.warn(
+            "tls",
+            "built without the `tls` feature; listener will be plain HTTP",
+        ),
+        Some(_) => report.fail(
+            "tls",
+            "[server.tls] is set but this binary was built without the `tls` feature",
+        ),
+        None => {}
+    }
+}
+
+/// Surface the insecure configurations `serve` warns about at startup.
+fn check_security(config: &config::GatewayConfig, report: &mut Report) {
+    if matches!(config.auth.mode, config::AuthMode::Iap(_))
+        && !config.server.trusted_proxies.is_configured()
+    {
+        report.warn(
+            "auth",
+            "IAP auth without [server.trusted_proxies]; identity headers can be spoofed",
+        );
+    }
+    if !config.auth.is_auth_enabled() {
+        if config.server.host.is_loopback() {
+            report.warn("auth", "no authentication configured");
+        } else {
+            report.warn(
+                "auth",
+                format!(
+                    "no authentication configured and bound to {}",
+                    config.server.host
+                ),
+            );
+        }
+    }
+    if !config.auth.rbac.enabled {
+        report.warn("rbac", "disabled; all authorization checks pass");
+    }
+}
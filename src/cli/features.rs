@@ -1,111 +1,131 @@
+/// Compile-time features as `(name, group, enabled)`, in display order.
+const FEATURES: &[(&str, &str, bool)] = &[
+    // Providers
+    (
+        "provider-openai",
+        "Providers",
+        cfg!(feature = "provider-openai"),
+    ),
+    (
+        "provider-anthropic",
+        "Providers",
+        cfg!(feature = "provider-anthropic"),
+    ),
+    (
+        "provider-test",
+        "Providers",
+        cfg!(feature = "provider-test"),
+    ),
+    (
+        "provider-bedrock",
+        "Providers",
+        cfg!(feature = "provider-bedrock"),
+    ),
+    (
+        "provider-vertex",
+        "Providers",
+        cfg!(feature = "provider-vertex"),
+    ),
+    (
+        "provider-azure",
+        "Providers",
+        cfg!(feature = "provider-azure"),
+    ),
+    // Assets
+    ("embed-ui", "Assets", cfg!(feature = "embed-ui")),
+    ("embed-docs", "Assets", cfg!(feature = "embed-docs")),
+    ("embed-catalog", "Assets", cfg!(feature = "embed-catalog")),
+    // Databases
+    (
+        "database-sqlite",
+        "Databases",
+        cfg!(feature = "database-sqlite"),
+    ),
+    (
+        "database-postgres",
+        "Databases",
+        cfg!(feature = "database-postgres"),
+    ),
+    // Infrastructure
+    ("redis", "Infrastructure", cfg!(feature = "redis")),
+    ("otlp", "Infrastructure", cfg!(feature = "otlp")),
+    ("sso", "Infrastructure", cfg!(feature = "sso")),
+    ("saml", "Infrastructure", cfg!(feature = "saml")),
+    ("webauthn", "Infrastructure", cfg!(feature = "webauthn")),
+    ("tls", "Infrastructure", cfg!(feature = "tls")),
+    ("cel", "Infrastructure", cfg!(feature = "cel")),
+    (
+        "transform-plugins",
+        "Infrastructure",
+        cfg!(feature = "transform-plugins"),
+    ),
+    ("prometheus", "Infrastructure", cfg!(feature = "prometheus")),
+    ("graphql", "Infrastructure", cfg!(feature = "graphql")),
+    // Secrets
+    ("vault", "Secrets", cfg!(feature = "vault")),
+    ("secrets-aws", "Secrets", cfg!(feature = "secrets-aws")),
+    ("secrets-azure", "Secrets", cfg!(feature = "secrets-azure")),
+    ("secrets-gcp", "Secrets", cfg!(feature = "secrets-gcp")),
+    // Storage & Processing
+    (
+        "s3-storage",
+        "Storage & Processing",
+        cfg!(feature = "s3-storage"),
+    ),
+    (
+        "document-extraction-basic",
+        "Storage & Processing",
+        cfg!(feature = "document-extraction-basic"),
+    ),
+    (
+        "document-extraction-full",
+        "Storage & Processing",
+        cfg!(feature = "document-extraction-full"),
+    ),
+    (
+        "virus-scan",
+        "Storage & Processing",
+        cfg!(feature = "virus-scan"),
+    ),
+    // Validation & Export
+    (
+        "json-schema",
+        "Validation & Export",
+        cfg!(feature = "json-schema"),
+    ),
+    (
+        "response-validation",
+        "Validation & Export",
+        cfg!(feature = "response-validation"),
+    ),
+    (
+        "csv-export",
+        "Validation & Export",
+        cfg!(feature = "csv-export"),
+    ),
+    // Integrations
+    ("smtp", "Integrations", cfg!(feature = "smtp")),
+    // Tools
+    ("forecasting", "Tools", cfg!(feature = "forecasting")),
+    ("wizard", "Tools", cfg!(feature = "wizard")),
+    // Documentation
+    ("utoipa", "Documentation", cfg!(feature = "utoipa")),
+];
+
+/// Names of the compile-time features enabled in this binary.
+#[cfg(feature = "utoipa")]
+pub(crate) fn enabled_features() -> Vec<&'static str> {
+    FEATURES
+        .iter()
+        .filter(|(_, _, enabled)| *enabled)
+        .map(|(name, _, _)| *name)
+        .collect()
+}
+
 /// Print enabled compile-time features and build profile.
 pub(crate) fn run_features() {
     let version = env!("CARGO_PKG_VERSION");
 
-    // Check each feature at compile time
-    let features: &[(&str, &str, bool)] = &[
-        // Providers
-        (
-            "provider-openai",
-            "Providers",
-            cfg!(feature = "provider-openai"),
-        ),
-        (
-            "provider-anthropic",
-            "Providers",
-            cfg!(feature = "provider-anthropic"),
-        ),
-        (
-            "provider-test",
-            "Providers",
-            cfg!(feature = "provider-test"),
-        ),
-        (
-            "provider-bedrock",
-            "Providers",
-            cfg!(feature = "provider-bedrock"),
-        ),
-        (
-            "provider-vertex",
-            "Providers",
-            cfg!(feature = "provider-vertex"),
-        ),
-        (
-            "provider-azure",
-            "Providers",
-            cfg!(feature = "provider-azure"),
-        ),
-        // Assets
-        ("embed-ui", "Assets", cfg!(feature = "embed-ui")),
-        ("embed-docs", "Assets", cfg!(feature = "embed-docs")),
-        ("embed-catalog", "Assets", cfg!(feature = "embed-catalog")),
-        // Databases
-        (
-            "database-sqlite",
-            "Databases",
-            cfg!(feature = "database-sqlite"),
-        ),
-        (
-            "database-postgres",
-            "Databases",
-            cfg!(feature = "database-postgres"),
-        ),
-        // Infrastructure
-        ("redis", "Infrastructure", cfg!(feature = "redis")),
-        ("otlp", "Infrastructure", cfg!(feature = "otlp")),
-        ("sso", "Infrastructure", cfg!(feature = "sso")),
-        ("saml", "Infrastructure", cfg!(feature = "saml")),
-        ("cel", "Infrastructure", cfg!(feature = "cel")),
-        ("prometheus", "Infrastructure", cfg!(feature = "prometheus")),
-        // Secrets
-        ("vault", "Secrets", cfg!(feature = "vault")),
-        ("secrets-aws", "Secrets", cfg!(feature = "secrets-aws")),
-        ("secrets-azure", "Secrets", cfg!(feature = "secrets-azure")),
-        ("secrets-gcp", "Secrets", cfg!(feature = "secrets-gcp")),
-        // Storage & Processing
-        (
-            "s3-storage",
-            "Storage & Processing",
-            cfg!(feature = "s3-storage"),
-        ),
-        (
-            "document-extraction-basic",
-            "Storage & Processing",
-            cfg!(feature = "document-extraction-basic"),
-        ),
-        (
-            "document-extraction-full",
-            "Storage & Processing",
-            cfg!(feature = "document-extraction-full"),
-        ),
-        (
-            "virus-scan",
-            "Storage & Processing",
-            cfg!(feature = "virus-scan"),
-        ),
-        // Validation & Export
-        (
-            "json-schema",
-            "Validation & Export",
-            cfg!(feature = "json-schema"),
-        ),
-        (
-            "response-validation",
-            "Validation & Export",
-            cfg!(feature = "response-validation"),
-        ),
-        (
-            "csv-export",
-            "Validation & Export",
-            cfg!(feature = "csv-export"),
-        ),
-        // Tools
-        ("forecasting", "Tools", cfg!(feature = "forecasting")),
-        ("wizard", "Tools", cfg!(feature = "wizard")),
-        // Documentation
-        ("utoipa", "Documentation", cfg!(feature = "utoipa")),
-    ];
-
     // Infer build profile from enabled features
     let profile = if cfg!(feature = "full") {
         "full"
@@ -124,12 +144,14 @@ pub(crate) fn run_features() {
     println!("Hadrian Gateway v{version}\n");
     println!("Build profile: {profile}");
     match profile {
-        "full" => println!("  (full = standard + saml, doc-extraction-full, virus-scan)\n"),
+        "full" => {
+            println!("  (full = standard + saml, webauthn, doc-extraction-full, virus-scan)\n")
+        }
         "headless" => {
             println!("  (headless = full features without embedded assets — UI, docs, catalog)\n")
         }
         "standard" => println!(
-            "  (standard = minimal + redis, otlp, doc-extraction-basic, postgres, embed-docs, prometheus, cel, utoipa, sso, forecasting, json-schema, response-validation, csv-export)\n"
+            "  (standard = minimal + redis, otlp, doc-extraction-basic, postgres, embed-docs, prometheus, cel, utoipa, sso, forecasting, json-schema, response-validation, csv-export, smtp)\n"
         ),
         "minimal" => {
             println!("  (minimal = tiny + sqlite, embed-catalog, embed-ui, wizard)\n")
@@ -145,7 +167,7 @@ pub(crate) fn run_features() {
     println!("Compile-time features:");
 
     let mut current_group = "";
-    for &(name, group, enabled) in features {
+    for &(name, group, enabled) in FEATURES {
         if group != current_group {
             if !current_group.is_empty() {
                 println!();
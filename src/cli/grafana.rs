@@ -0,0 +1,68 @@
+use std::path::Path;
+
+use super::resolve_config_path;
+use crate::{config, observability::grafana};
+
+/// Dashboard file name written by `--output-dir`.
+const DASHBOARD_FILE: &str = "hadrian-gateway.json";
+/// Alert rules file name written by `--output-dir`.
+const ALERT_RULES_FILE: &str = "hadrian-alerts.json";
+
+/// Generate the Grafana dashboard and Prometheus alert rules for the
+/// configured providers.
+///
+/// With `output_dir`, writes `hadrian-gateway.json` (drop into a Grafana
+/// file-based dashboard provider) and `hadrian-alerts.json` (reference from
+/// Prometheus `rule_files`; JSON is valid YAML). Without it, prints both as a
+/// single JSON object to stdout, matching `GET /admin/v1/observability/grafana`.
+pub(crate) fn run_grafana_export(explicit_config_path: Option<&str>, output_dir: Option<String>) {
+    let (config_path, _) = match resolve_config_path(explicit_config_path) {
+        Ok((path, is_new)) => (path, is_new),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let config = match config::GatewayConfig::from_file(&config_path) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!(
+                "Failed to load config from {}: {}",
+                config_path.display(),
+                e
+            );
+            std::process::exit(1);
+        }
+    };
+
+    let dashboard = grafana::dashboard(&config.providers);
+    let alert_rules = grafana::alert_rules(&config.providers, &config.observability.slos);
+
+    match output_dir {
+        Some(dir) => {
+            let dir = Path::new(&dir);
+            std::fs::create_dir_all(dir)
+                .unwrap_or_else(|e| panic!("Failed to create {}: {}", dir.display(), e));
+            for (file, value) in [
+                (DASHBOARD_FILE, &dashboard),
+                (ALERT_RULES_FILE, &alert_rules),
+            ] {
+                let path = dir.join(file);
+                let content =
+                    serde_json::to_string_pretty(value).expect("Failed to serialize to JSON");
+                std::fs::write(&path, content)
+                    .unwrap_or_else(|e| panic!("Failed to write to {}: {}", path.display(), e));
+                eprintln!("Wrote {}", path.display());
+            }
+        }
+        None => {
+            let content = serde_json::to_string_pretty(&serde_json::json!({
+                "dashboard": dashboard,
+                "alert_rules": alert_rules,
+            }))
+            .expect("Failed to serialize to JSON");
+            println!("{}", content);
+        }
+    }
+}
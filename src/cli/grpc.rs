@@ -0,0 +1,291 @@
+//! gRPC data-plane listener.
+//!
+//! Serves `hadrian.v1.Gateway` (see `proto/hadrian/v1/gateway.proto`) on the
+//! `[server.grpc]` port. Each call is turned into an HTTP request to the same
+//! `Router` the HTTP listener serves: call metadata becomes headers, the JSON
+//! body is passed through, and the peer address is inserted as `ConnectInfo`.
+//! API key auth, limits, guardrails and usage logging are therefore exactly
+//! those of the HTTP API. Streaming calls read the SSE response and send each
+//! `data:` event as one message.
+
+use std::{collections::VecDeque, future::Future, net::SocketAddr, pin::Pin};
+
+use axum::{Router, body::Body, extract::ConnectInfo};
+use bytes::{Bytes, BytesMut};
+use futures::{Stream, StreamExt};
+use http::{HeaderMap, HeaderValue, Method, StatusCode, header};
+use tonic::{Code, Request, Response, Status};
+use tower::ServiceExt;
+
+mod proto {
+    tonic::include_proto!("hadrian.v1");
+}
+
+use proto::{
+    ChatCompletionChunk, ChatCompletionRequest, ChatCompletionResponse, EmbeddingRequest,
+    EmbeddingResponse,
+    gateway_server::{Gateway, GatewayServer},
+};
+
+const CHAT_COMPLETIONS_PATH: &str = "/v1/chat/completions";
+const EMBEDDINGS_PATH: &str = "/v1/embeddings";
+
+/// Metadata describing the gRPC transport rather than the call. Not forwarded.
+const TRANSPORT_METADATA: &[&str] = &["content-type", "content-length", "te", "user-agent"];
+
+/// Serve the gRPC API until `shutdown` resolves.
+pub(super) async fn serve(
+    addr: SocketAddr,
+    app: Router,
+    shutdown: impl Future<Output = ()>,
+) -> Result<(), tonic::transport::Error> {
+    tonic::transport::Server::builder()
+        .add_service(GatewayServer::new(GatewayService { app }))
+        .serve_with_shutdown(addr, shutdown)
+        .await
+}
+
+struct GatewayService {
+    app: Router,
+}
+
+#[tonic::async_trait]
+impl Gateway for GatewayService {
+    async fn create_chat_completion(
+        &self,
+        request: Request<ChatCompletionRequest>,
+    ) -> Result<Response<ChatCompletionResponse>, Status> {
+        let remote_addr = request.remote_addr();
+        let (metadata, _, message) = request.into_parts();
+        let body = with_stream_flag(&message.json, false)?;
+        let response = self
+            .forward(
+                CHAT_COMPLETIONS_PATH,
+                metadata.into_headers(),
+                remote_addr,
+                body,
+            )
+            .await?;
+        let json = read_body(response.into_body()).await?;
+        Ok(Response::new(ChatCompletionResponse {
+            json: json.to_vec(),
+        }))
+    }
+
+    type StreamChatCompletionStream =
+        Pin<Box<dyn Stream<Item = Result<ChatCompletionChunk, Status>> + Send>>;
+
+    async fn stream_chat_completion(
+        &self,
+        request: Request<ChatCompletionRequest>,
+    ) -> Result<Response<Self::StreamChatCompletionStream>, Status> {
+        let remote_addr = request.remote_addr();
+        let (metadata, _, message) = request.into_parts();
+        let body = with_stream_flag(&message.json, true)?;
+        let response = self
+            .forward(
+                CHAT_COMPLETIONS_PATH,
+                metadata.into_headers(),
+                remote_addr,
+                body,
+            )
+            .await?;
+        let chunks = sse_events(response.into_body()).map(|event| {
+            event.map(|json| ChatCompletionChunk {
+                json: json.to_vec(),
+            })
+        });
+        Ok(Response::new(Box::pin(chunks)))
+    }
+
+    async fn create_embedding(
+        &self,
+        request: Request<EmbeddingRequest>,
+    ) -> Result<Response<EmbeddingResponse>, Status> {
+        let remote_addr = request.remote_addr();
+        let (metadata, _, message) = request.into_parts();
+        let response = self
+            .forward(
+                EMBEDDINGS_PATH,
+                metadata.into_headers(),
+                remote_addr,
+                message.json,
+            )
+            .await?;
+        let json = read_body(response.into_body()).await?;
+        Ok(Response::new(EmbeddingResponse {
+            json: json.to_vec(),
+        }))
+    }
+}
+
+impl GatewayService {
+    /// POST `body` to `path` on the HTTP router. Error responses become a
+    /// `Status` carrying the JSON error body as details.
+    async fn forward(
+        &self,
+        path: &str,
+        metadata: HeaderMap,
+        remote_addr: Option<SocketAddr>,
+        body: Vec<u8>,
+    ) -> Result<http::Response<Body>, Status> {
+        let mut request = http::Request::builder()
+            .method(Method::POST)
+            .uri(path)
+            .body(Body::from(body))
+            .map_err(|e| Status::internal(format!("failed to build request: {e}")))?;
+
+        let headers = request.headers_mut();
+        for (name, value) in &metadata {
+            let name_str = name.as_str();
+            if TRANSPORT_METADATA.contains(&name_str)
+                || name_str.starts_with("grpc-")
+                || name_str.ends_with("-bin")
+            {
+                continue;
+            }
+            headers.append(name.clone(), value.clone());
+        }
+        headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+        if let Some(addr) = remote_addr {
+            request.extensions_mut().insert(ConnectInfo(addr));
+        }
+
+        let response = match self.app.clone().oneshot(request).await {
+            Ok(response) => response,
+            Err(never) => match never {},
+        };
+        if response.status().is_success() {
+            return Ok(response);
+        }
+
+        let status = response.status();
+        let body = read_body(response.into_body()).await.unwrap_or_default();
+        let message = serde_json::from_slice::<serde_json::Value>(&body)
+            .ok()
+            .and_then(|v| v["error"]["message"].as_str().map(str::to_string))
+            .unwrap_or_else(|| {
+                status
+                    .canonical_reason()
+                    .unwrap_or("request failed")
+                    .to_string()
+            });
+        Err(Status::with_details(grpc_code(status), message, body))
+    }
+}
+
+/// Set `stream` on a JSON request body.
+fn with_stream_flag(json: &[u8], stream: bool) -> Result<Vec<u8>, Status> {
+    let mut body: serde_json::Value = serde_json::from_slice(json)
+        .map_err(|e| Status::invalid_argument(format!("invalid JSON body: {e}")))?;
+    let Some(object) = body.as_object_mut() else {
+        return Err(Status::invalid_argument(
+            "request body must be a JSON object",
+        ));
+    };
+    object.insert("stream".to_string(), serde_json::Value::Bool(stream));
+    serde_json::to_vec(&body).map_err(|e| Status::internal(e.to_string()))
+}
+
+async fn read_body(body: Body) -> Result<Bytes, Status> {
+    // The router already enforces response size limits
+    axum::body::to_bytes(body, usize::MAX)
+        .await
+        .map_err(|e| Status::internal(format!("failed to read response: {e}")))
+}
+
+/// The gRPC code for an HTTP error status, following the standard mapping.
+fn grpc_code(status: StatusCode) -> Code {
+    match status.as_u16() {
+        400 | 413 | 422 => Code::InvalidArgument,
+        401 => Code::Unauthenticated,
+        402 | 429 => Code::ResourceExhausted,
+        403 => Code::PermissionDenied,
+        404 => Code::NotFound,
+        408 | 504 => Code::DeadlineExceeded,
+        409 => Code::Aborted,
+        499 => Code::Cancelled,
+        501 => Code::Unimplemented,
+        502 | 503 => Code::Unavailable,
+        400..=499 => Code::FailedPrecondition,
+        _ => Code::Internal,
+    }
+}
+
+/// The `data:` payloads of an SSE body, excluding the final `[DONE]`.
+///
+/// The body is read to the end even after `[DONE]`, so anything the router
+/// does when a stream completes (usage logging) still runs.
+fn sse_events(body: Body) -> impl Stream<Item = Result<Bytes, Status>> + Send {
+    struct State {
+        body: axum::body::BodyDataStream,
+        buffer: BytesMut,
+        data: Vec<u8>,
+        events: VecDeque<Bytes>,
+        done: bool,
+    }
+
+    impl State {
+        /// Move complete lines from `buffer` into `events`.
+        fn parse_lines(&mut self) {
+            while let Some(end) = self.buffer.iter().position(|b| *b == b'\n') {
+                let line = self.buffer.split_to(end + 1);
+                let line = line
+                    .strip_suffix(b"\n")
+                    .map(|l| l.strip_suffix(b"\r").unwrap_or(l))
+                    .unwrap_or(&line);
+
+                if line.is_empty() {
+                    let data = std::mem::take(&mut self.data);
+                    if !data.is_empty() && data != b"[DONE]" {
+                        self.events.push_back(Bytes::from(data));
+                    }
+                } else if let Some(value) = line.strip_prefix(b"data:") {
+                    if !self.data.is_empty() {
+                        self.data.push(b'\n');
+                    }
+                    self.data
+                        .extend_from_slice(value.strip_prefix(b" ").unwrap_or(value));
+                }
+            }
+        }
+    }
+
+    let state = State {
+        body: body.into_data_stream(),
+        buffer: BytesMut::new(),
+        data: Vec::new(),
+        events: VecDeque::new(),
+        done: false,
+    };
+
+    futures::stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(event) = state.events.pop_front() {
+                return Some((Ok(event), state));
+            }
+            if state.done {
+                return None;
+            }
+            match state.body.next().await {
+                Some(Ok(chunk)) => {
+                    state.buffer.extend_from_slice(&chunk);
+                    state.parse_lines();
+                }
+                Some(Err(e)) => {
+                    state.done = true;
+                    return Some((Err(Status::internal(format!("stream failed: {e}"))), state));
+                }
+                None => {
+                    // Flush an event the body didn't terminate
+                    state.buffer.extend_from_slice(b"\n\n");
+                    state.parse_lines();
+                    state.done = true;
+                }
+            }
+        }
+    })
+}
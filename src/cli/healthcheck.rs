@@ -12,8 +12,8 @@ pub async fn run_healthcheck(
 ) {
     let url = match url_override {
         Some(u) => u,
-        None => match resolve_url_from_config(config_path) {
-            Ok(u) => u,
+        None => match local_base_url(config_path) {
+            Ok(base) => format!("{base}/health/live"),
             Err(err) => {
                 eprintln!("healthcheck: could not resolve URL from config: {err}");
                 std::process::exit(1);
@@ -47,7 +47,9 @@ pub async fn run_healthcheck(
     }
 }
 
-fn resolve_url_from_config(config_path: Option<&str>) -> Result<String, String> {
+/// `http://host:port` of the gateway described by the config file, for
+/// subcommands that talk to a locally running instance.
+pub(super) fn local_base_url(config_path: Option<&str>) -> Result<String, String> {
     let path = config_path.ok_or_else(|| {
         "no --config supplied and no --url override; pass one of them".to_string()
     })?;
@@ -65,5 +67,5 @@ fn resolve_url_from_config(config_path: Option<&str>) -> Result<String, String>
             }
         }
     };
-    Ok(format!("http://{host}:{}/health/live", config.server.port))
+    Ok(format!("http://{host}:{}", config.server.port))
 }
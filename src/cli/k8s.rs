@@ -0,0 +1,489 @@
+//! `hadrian export k8s` subcommand.
+//!
+//! Renders Kubernetes manifests, or a values file for the Helm chart in
+//! `helm/hadrian`, from the raw config file. `${VAR}` references are kept as
+//! they are and become keys of a Secret injected as environment variables, so
+//! no secret from the local environment ends up in the output unless
+//! `--from-env` asks for it.
+
+use std::{collections::BTreeMap, net::IpAddr, path::Path};
+
+use serde_json::{Value, json};
+
+use super::resolve_config_path;
+
+/// Image used when `--image` isn't given.
+const DEFAULT_IMAGE_REPOSITORY: &str = "ghcr.io/hadriangateway/hadrian";
+/// Where the Docker image reads its config from.
+const CONFIG_DIR: &str = "/app/config";
+/// Where the Docker image keeps SQLite data.
+const DATA_DIR: &str = "/app/data";
+/// Must exceed the default `[server.shutdown]` drain budget (55s), as in the
+/// Helm chart.
+const TERMINATION_GRACE_PERIOD_SECS: u64 = 60;
+/// Value of Secret keys the environment doesn't provide.
+const SECRET_PLACEHOLDER: &str = "CHANGE_ME";
+
+/// Options of `hadrian export k8s`.
+#[derive(Debug)]
+pub(crate) struct K8sExportOptions {
+    pub name: String,
+    pub namespace: Option<String>,
+    pub image: Option<String>,
+    pub replicas: u32,
+    pub helm: bool,
+    pub from_env: bool,
+    pub output_dir: Option<String>,
+}
+
+/// A rendered file and the documents it holds.
+struct Rendered {
+    file: &'static str,
+    documents: Vec<Value>,
+}
+
+pub(crate) fn run_k8s_export(explicit_config_path: Option<&str>, options: K8sExportOptions) {
+    let config_path = match resolve_config_path(explicit_config_path) {
+        Ok((path, _)) => path,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let raw = match std::fs::read_to_string(&config_path) {
+        Ok(raw) => raw,
+        Err(e) => {
+            eprintln!("Failed to read {}: {}", config_path.display(), e);
+            std::process::exit(1);
+        }
+    };
+    let mut config: toml::Table = match toml::from_str(&raw) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Failed to parse {}: {}", config_path.display(), e);
+            std::process::exit(1);
+        }
+    };
+
+    let mut warnings = Vec::new();
+    adapt_for_container(&mut config, &mut warnings);
+    let env = env_references(&raw);
+
+    let rendered = if options.helm {
+        render_helm(&config, &env, &options, &mut warnings)
+    } else {
+        render_manifests(&config, &env, &options, &mut warnings)
+    };
+
+    for warning in &warnings {
+        eprintln!("Warning: {}", warning);
+    }
+
+    match &options.output_dir {
+        Some(dir) => {
+            let dir = Path::new(dir);
+            std::fs::create_dir_all(dir)
+                .unwrap_or_else(|e| panic!("Failed to create {}: {}", dir.display(), e));
+            for file in &rendered {
+                let path = dir.join(file.file);
+                std::fs::write(&path, to_yaml(&file.documents))
+                    .unwrap_or_else(|e| panic!("Failed to write to {}: {}", path.display(), e));
+                eprintln!("Wrote {}", path.display());
+            }
+        }
+        // A values file must stay a single document, so the Secret is only
+        // written with `--output-dir`
+        None if options.helm => {
+            if rendered.len() > 1 {
+                eprintln!("Note: use --output-dir to also write the Secret the values refer to");
+            }
+            print!("{}", to_yaml(&rendered[0].documents));
+        }
+        None => {
+            let documents: Vec<_> = rendered.into_iter().flat_map(|f| f.documents).collect();
+            print!("{}", to_yaml(&documents));
+        }
+    }
+}
+
+/// Serialize documents as a multi-document YAML stream.
+fn to_yaml(documents: &[Value]) -> String {
+    documents
+        .iter()
+        .map(|doc| serde_yaml_ng::to_string(doc).expect("Failed to serialize to YAML"))
+        .collect::<Vec<_>>()
+        .join("---\n")
+}
+
+/// Make a config written for a workstation work inside a pod: listen on all
+/// interfaces and keep SQLite data on the data volume.
+fn adapt_for_container(config: &mut toml::Table, warnings: &mut Vec<String>) {
+    if let Some(server) = config.get_mut("server").and_then(|s| s.as_table_mut()) {
+        let loopback = server
+            .get("host")
+            .and_then(|v| v.as_str())
+            .and_then(|h| h.parse::<IpAddr>().ok())
+            .is_some_and(|ip| ip.is_loopback());
+        if loopback {
+            server.insert("host".into(), "0.0.0.0".into());
+            warnings.push("server.host is a loopback address; listening on 0.0.0.0 instead".into());
+        }
+    }
+
+    if let Some(database) = config.get_mut("database").and_then(|d| d.as_table_mut())
+        && database.get("type").and_then(|v| v.as_str()) == Some("sqlite")
+    {
+        let outside_volume = database
+            .get("path")
+            .and_then(|v| v.as_str())
+            .is_none_or(|p| !p.starts_with(DATA_DIR));
+        if outside_volume {
+            database.insert("path".into(), format!("{DATA_DIR}/hadrian.db").into());
+        }
+        warnings.push(format!(
+            "SQLite keeps data in {DATA_DIR} on a single pod; use PostgreSQL to run more than one replica"
+        ));
+    }
+}
+
+/// Environment variables referenced as `${VAR}` or `${VAR:-default}`
+/// outside comments, mapped to whether they are required.
+fn env_references(raw: &str) -> BTreeMap<String, bool> {
+    let re = regex::Regex::new(r"\$\{([^}]+)\}").unwrap();
+    let mut vars = BTreeMap::new();
+    for line in raw.lines() {
+        let code = line.split('#').next().unwrap_or_default();
+        for cap in re.captures_iter(code) {
+            let (name, required) = match cap[1].split_once(":-") {
+                Some((name, _)) => (name, false),
+                None => (&cap[1], true),
+            };
+            *vars.entry(name.to_string()).or_insert(false) |= required;
+        }
+    }
+    vars
+}
+
+fn secret_name(options: &K8sExportOptions) -> String {
+    format!("{}-env", options.name)
+}
+
+fn image(options: &K8sExportOptions) -> String {
+    options
+        .image
+        .clone()
+        .unwrap_or_else(|| format!("{DEFAULT_IMAGE_REPOSITORY}:{}", env!("CARGO_PKG_VERSION")))
+}
+
+fn port(config: &toml::Table) -> i64 {
+    config
+        .get("server")
+        .and_then(|s| s.get("port"))
+        .and_then(|p| p.as_integer())
+        .unwrap_or(8080)
+}
+
+fn metadata(options: &K8sExportOptions, name: String) -> Value {
+    let mut metadata = json!({
+        "name": name,
+        "labels": labels(options),
+    });
+    if let Some(namespace) = &options.namespace {
+        metadata["namespace"] = namespace.clone().into();
+    }
+    metadata
+}
+
+fn labels(options: &K8sExportOptions) -> Value {
+    json!({
+        "app.kubernetes.io/name": "hadrian",
+        "app.kubernetes.io/instance": options.name,
+    })
+}
+
+/// The Secret holding the referenced environment variables. Optional
+/// variables the environment doesn't set are left out so their defaults
+/// apply.
+fn secret(
+    env: &BTreeMap<String, bool>,
+    options: &K8sExportOptions,
+    warnings: &mut Vec<String>,
+) -> Value {
+    let mut missing = Vec::new();
+    let mut data = serde_json::Map::new();
+    for (var, &required) in env {
+        let value = options.from_env.then(|| std::env::var(var).ok()).flatten();
+        match value {
+            Some(value) => {
+                data.insert(var.clone(), value.into());
+            }
+            None if required => {
+                missing.push(var.as_str());
+                data.insert(var.clone(), SECRET_PLACEHOLDER.into());
+            }
+            None => {}
+        }
+    }
+    if !missing.is_empty() {
+        warnings.push(format!(
+            "Secret {} has placeholder values for {}; fill them in before applying",
+            secret_name(options),
+            missing.join(", ")
+        ));
+    }
+    json!({
+        "apiVersion": "v1",
+        "kind": "Secret",
+        "metadata": metadata(options, secret_name(options)),
+        "type": "Opaque",
+        "stringData": data,
+    })
+}
+
+fn render_manifests(
+    config: &toml::Table,
+    env: &BTreeMap<String, bool>,
+    options: &K8sExportOptions,
+    warnings: &mut Vec<String>,
+) -> Vec<Rendered> {
+    let config_map_name = format!("{}-config", options.name);
+    let hadrian_toml = toml::to_string_pretty(config).expect("Failed to serialize config");
+    let sqlite = config
+        .get("database")
+        .and_then(|d| d.get("type"))
+        .and_then(|t| t.as_str())
+        == Some("sqlite");
+    if sqlite && options.replicas > 1 {
+        warnings.push("SQLite doesn't support more than one replica".into());
+    }
+
+    let config_map = json!({
+        "apiVersion": "v1",
+        "kind": "ConfigMap",
+        "metadata": metadata(options, config_map_name.clone()),
+        "data": {"hadrian.toml": hadrian_toml},
+    });
+
+    let mut volumes = vec![json!({"name": "config", "configMap": {"name": config_map_name}})];
+    let mut volume_mounts =
+        vec![json!({"name": "config", "mountPath": CONFIG_DIR, "readOnly": true})];
+    if sqlite {
+        volumes.push(json!({"name": "data", "emptyDir": {}}));
+        volume_mounts.push(json!({"name": "data", "mountPath": DATA_DIR}));
+    }
+    let mut container = json!({
+        "name": "hadrian",
+        "image": image(options),
+        "args": ["--config", format!("{CONFIG_DIR}/hadrian.toml")],
+        "ports": [{"name": "http", "containerPort": port(config), "protocol": "TCP"}],
+        "livenessProbe": {
+            "httpGet": {"path": "/health/live", "port": "http"},
+            "initialDelaySeconds": 10,
+            "periodSeconds": 30,
+            "timeoutSeconds": 3,
+        },
+        "readinessProbe": {
+            "httpGet": {"path": "/health/ready", "port": "http"},
+            "initialDelaySeconds": 5,
+            "periodSeconds": 10,
+            "timeoutSeconds": 3,
+        },
+        "securityContext": {
+            "runAsNonRoot": true,
+            "runAsUser": 1000,
+            "allowPrivilegeEscalation": false,
+            "readOnlyRootFilesystem": true,
+            "capabilities": {"drop": ["ALL"]},
+        },
+        "volumeMounts": volume_mounts,
+    });
+    let mut rendered = Vec::new();
+    if !env.is_empty() {
+        container["envFrom"] = json!([{"secretRef": {"name": secret_name(options)}}]);
+        rendered.push(Rendered {
+            file: "secret.yaml",
+            documents: vec![secret(env, options, warnings)],
+        });
+    }
+
+    let deployment = json!({
+        "apiVersion": "apps/v1",
+        "kind": "Deployment",
+        "metadata": metadata(options, options.name.clone()),
+        "spec": {
+            "replicas": options.replicas,
+            "selector": {"matchLabels": labels(options)},
+            "template": {
+                "metadata": {"labels": labels(options)},
+                "spec": {
+                    "terminationGracePeriodSeconds": TERMINATION_GRACE_PERIOD_SECS,
+                    "securityContext": {"fsGroup": 1000},
+                    "containers": [container],
+                    "volumes": volumes,
+                },
+            },
+        },
+    });
+    let service = json!({
+        "apiVersion": "v1",
+        "kind": "Service",
+        "metadata": metadata(options, options.name.clone()),
+        "spec": {
+            "type": "ClusterIP",
+            "selector": labels(options),
+            "ports": [{"name": "http", "port": 80, "targetPort": "http", "protocol": "TCP"}],
+        },
+    });
+
+    rendered.insert(
+        0,
+        Rendered {
+            file: "configmap.yaml",
+            documents: vec![config_map],
+        },
+    );
+    rendered.push(Rendered {
+        file: "deployment.yaml",
+        documents: vec![deployment],
+    });
+    rendered.push(Rendered {
+        file: "service.yaml",
+        documents: vec![service],
+    });
+    rendered
+}
+
+/// Providers the Helm chart has values for, by name and `type`.
+const HELM_PROVIDERS: &[(&str, &str)] = &[
+    ("openrouter", "open_ai"),
+    ("openai", "open_ai"),
+    ("anthropic", "anthropic"),
+];
+
+/// Sections the Helm chart renders itself. Everything else in the config
+/// can't be expressed in its values.
+const HELM_SECTIONS: &[&str] = &["server", "database", "cache", "providers"];
+
+fn render_helm(
+    config: &toml::Table,
+    env: &BTreeMap<String, bool>,
+    options: &K8sExportOptions,
+    warnings: &mut Vec<String>,
+) -> Vec<Rendered> {
+    let (repository, tag) = match &options.image {
+        Some(image) => match image.rsplit_once(':').filter(|(_, t)| !t.contains('/')) {
+            Some((repository, tag)) => (repository.to_string(), tag.to_string()),
+            None => (image.clone(), String::new()),
+        },
+        None => (
+            DEFAULT_IMAGE_REPOSITORY.to_string(),
+            env!("CARGO_PKG_VERSION").to_string(),
+        ),
+    };
+    let get = |section: &str, key: &str| {
+        config
+            .get(section)
+            .and_then(|s| s.get(key))
+            .and_then(|v| v.as_str())
+            .map(String::from)
+    };
+
+    let database = match get("database", "type").as_deref() {
+        Some("sqlite") => json!({"type": "sqlite"}),
+        Some("postgres") => {
+            let mut postgres = json!({"url": get("database", "url").unwrap_or_default()});
+            if let Some(read_url) = get("database", "read_url") {
+                postgres["readUrl"] = read_url.into();
+            }
+            json!({"type": "postgres", "postgres": postgres})
+        }
+        _ => {
+            warnings.push("The Helm chart always configures a database; using SQLite".into());
+            json!({"type": "sqlite"})
+        }
+    };
+    let cache = match get("cache", "type").as_deref() {
+        Some("redis") => json!({
+            "type": "redis",
+            "redis": {"url": get("cache", "url").unwrap_or_default()},
+        }),
+        _ => json!({"type": "memory"}),
+    };
+
+    let mut providers = serde_json::Map::new();
+    if let Some(default) = get("providers", "default_provider") {
+        providers.insert("defaultProvider".into(), default.into());
+    }
+    let configured = config.get("providers").and_then(|p| p.as_table());
+    for (name, provider) in configured.into_iter().flatten() {
+        let Some(provider) = provider.as_table() else {
+            continue;
+        };
+        let provider_type = provider.get("type").and_then(|t| t.as_str());
+        if !HELM_PROVIDERS.contains(&(name.as_str(), provider_type.unwrap_or_default())) {
+            warnings.push(format!(
+                "Provider '{name}' has no Helm chart values; render plain manifests to keep it"
+            ));
+            continue;
+        }
+        let mut values = json!({"enabled": true});
+        if let Some(base_url) = provider.get("base_url").and_then(|v| v.as_str()) {
+            values["baseUrl"] = base_url.into();
+        }
+        if let Some(timeout) = provider.get("timeout_secs").and_then(|v| v.as_integer()) {
+            values["timeoutSecs"] = timeout.into();
+        }
+        // `${VAR}` API keys come from the generated Secret
+        let key_var = provider
+            .get("api_key")
+            .and_then(|v| v.as_str())
+            .and_then(|k| k.strip_prefix("${"))
+            .and_then(|k| k.strip_suffix('}'))
+            .map(|k| k.split(":-").next().unwrap_or(k));
+        match key_var {
+            Some(var) => {
+                values["existingSecret"] = secret_name(options).into();
+                values["existingSecretKey"] = var.into();
+            }
+            None => warnings.push(format!(
+                "Provider '{name}' has a literal API key; set gateway.providers.{name}.apiKey or existingSecret"
+            )),
+        }
+        providers.insert(name.clone(), values);
+    }
+
+    for section in config.keys() {
+        if !HELM_SECTIONS.contains(&section.as_str()) {
+            warnings.push(format!(
+                "[{section}] has no Helm chart values; render plain manifests to keep it"
+            ));
+        }
+    }
+
+    let mut values = json!({
+        "replicaCount": options.replicas,
+        "image": {"repository": repository, "tag": tag},
+        "gateway": {
+            "server": {"port": port(config)},
+            "database": database,
+            "cache": cache,
+            "providers": providers,
+        },
+    });
+    let mut rendered = Vec::new();
+    if !env.is_empty() {
+        values["extraEnvFrom"] = json!([{"secretRef": {"name": secret_name(options)}}]);
+        rendered.push(Rendered {
+            file: "secret.yaml",
+            documents: vec![secret(env, options, warnings)],
+        });
+    }
+    rendered.insert(
+        0,
+        Rendered {
+            file: "values.yaml",
+            documents: vec![values],
+        },
+    );
+    rendered
+}
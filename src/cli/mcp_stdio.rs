@@ -0,0 +1,140 @@
+//! `hadrian mcp-stdio` subcommand.
+//!
+//! Bridges an MCP client that only speaks the stdio transport (IDEs, desktop
+//! agents) to a running gateway's `/v1/mcp` endpoint. Each line on stdin is a
+//! JSON-RPC message; it's POSTed with the API key from `HADRIAN_API_KEY` and
+//! the reply is written to stdout as a single line. Diagnostics go to stderr
+//! so they never corrupt the protocol stream.
+
+use serde_json::{Value, json};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+const API_KEY_ENV: &str = "HADRIAN_API_KEY";
+
+pub async fn run_mcp_stdio(config_path: Option<&str>, url_override: Option<String>) {
+    let url = match url_override {
+        Some(u) => u,
+        None => match super::healthcheck::local_base_url(config_path) {
+            Ok(base) => format!("{base}/v1/mcp"),
+            Err(err) => {
+                eprintln!("mcp-stdio: could not resolve URL from config: {err}");
+                std::process::exit(1);
+            }
+        },
+    };
+    let api_key = std::env::var(API_KEY_ENV).ok().filter(|k| !k.is_empty());
+    if api_key.is_none() {
+        eprintln!("mcp-stdio: {API_KEY_ENV} is not set; requests will be unauthenticated");
+    }
+
+    let client = reqwest::Client::new();
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut stdout = tokio::io::stdout();
+
+    loop {
+        let line = match lines.next_line().await {
+            Ok(Some(line)) => line,
+            Ok(None) => break,
+            Err(err) => {
+                eprintln!("mcp-stdio: failed to read stdin: {err}");
+                std::process::exit(1);
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let reply = match forward(&client, &url, api_key.as_deref(), &line).await {
+            Ok(reply) => reply,
+            Err(err) => {
+                eprintln!("mcp-stdio: {err}");
+                // Requests still need an answer, or the client waits forever
+                request_id(&line).map(|id| {
+                    json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "error": { "code": -32603, "message": err },
+                    })
+                })
+            }
+        };
+
+        if let Some(reply) = reply {
+            let mut out = reply.to_string();
+            out.push('\n');
+            if let Err(err) = stdout.write_all(out.as_bytes()).await {
+                eprintln!("mcp-stdio: failed to write stdout: {err}");
+                std::process::exit(1);
+            }
+            let _ = stdout.flush().await;
+        }
+    }
+}
+
+/// POST one message. Returns `None` for accepted notifications.
+async fn forward(
+    client: &reqwest::Client,
+    url: &str,
+    api_key: Option<&str>,
+    message: &str,
+) -> Result<Option<Value>, String> {
+    let mut request = client
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(
+            reqwest::header::ACCEPT,
+            "application/json, text/event-stream",
+        )
+        .body(message.to_string());
+    if let Some(key) = api_key {
+        request = request.bearer_auth(key);
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("request to {url} failed: {e}"))?;
+    let status = response.status();
+    if status == reqwest::StatusCode::ACCEPTED {
+        return Ok(None);
+    }
+    let is_sse = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("text/event-stream"));
+    let body = response
+        .text()
+        .await
+        .map_err(|e| format!("failed to read response: {e}"))?;
+
+    let payload = if is_sse {
+        body.lines()
+            .filter_map(|l| l.strip_prefix("data:"))
+            .map(str::trim_start)
+            .collect::<Vec<_>>()
+            .join("\n")
+    } else {
+        body
+    };
+    let value: Value = serde_json::from_str(&payload)
+        .map_err(|_| format!("{url} returned {status}: {payload}"))?;
+
+    // Gateway errors (auth, rate limits) use the API error shape, not JSON-RPC
+    if value.get("jsonrpc").is_none() {
+        let message = value["error"]["message"]
+            .as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| value.to_string());
+        return Err(format!("{url} returned {status}: {message}"));
+    }
+    Ok(Some(value))
+}
+
+fn request_id(message: &str) -> Option<Value> {
+    serde_json::from_str::<Value>(message)
+        .ok()?
+        .get("id")
+        .filter(|id| !id.is_null())
+        .cloned()
+}
@@ -1,13 +1,23 @@
 mod bootstrap;
+mod check;
 #[cfg(feature = "server")]
 mod container;
 mod features;
+mod grafana;
+#[cfg(feature = "grpc")]
+mod grpc;
 #[cfg(feature = "server")]
 mod healthcheck;
 mod init;
+#[cfg(feature = "server")]
+mod k8s;
+#[cfg(feature = "server")]
+mod mcp_stdio;
 mod migrate;
 mod openapi;
 mod server;
+#[cfg(feature = "tls")]
+mod tls;
 #[cfg(any(
     feature = "document-extraction-basic",
     feature = "document-extraction-full"
@@ -38,12 +48,23 @@ pub struct Args {
 #[derive(clap::Subcommand, Debug)]
 enum Command {
     /// Start the gateway server (default)
-    Serve,
-    /// Export the OpenAPI specification (JSON format)
+    Serve {
+        /// Initialize everything the server needs (database, cache, secrets,
+        /// providers) without listening, print a report and exit non-zero if
+        /// startup would fail
+        #[arg(long)]
+        check: bool,
+    },
+    /// Export the OpenAPI specification (JSON format), or generate a typed client SDK
     Openapi {
-        /// Output file (defaults to stdout)
+        /// Output file (defaults to stdout), or output directory with --generate
+        /// (defaults to ./hadrian-sdk-<language>)
         #[arg(short, long)]
         output: Option<String>,
+        /// Generate a client SDK instead of exporting the spec: typescript, python or go
+        #[cfg(feature = "utoipa")]
+        #[arg(long, value_name = "LANGUAGE")]
+        generate: Option<crate::sdk::SdkLanguage>,
     },
     /// Export the JSON schema for the configuration file
     Schema {
@@ -103,6 +124,22 @@ enum Command {
     },
     /// Show enabled compile-time features
     Features,
+    /// Generate a Grafana dashboard and Prometheus alert rules.
+    ///
+    /// Built from this binary's metric names and the providers in the config
+    /// file, so the assets stay in sync with the deployed gateway.
+    Grafana {
+        /// Directory to write `hadrian-gateway.json` and `hadrian-alerts.json`
+        /// into (defaults to printing both to stdout)
+        #[arg(short, long)]
+        output_dir: Option<String>,
+    },
+    /// Render deployment artifacts from the config file.
+    #[cfg(feature = "server")]
+    Export {
+        #[command(subcommand)]
+        target: ExportTarget,
+    },
     /// Probe the gateway's `/health/live` endpoint and exit with status.
     ///
     /// Used by the Docker `HEALTHCHECK` so the runtime image doesn't need to
@@ -118,6 +155,18 @@ enum Command {
         #[arg(long, default_value = "3")]
         timeout_secs: u64,
     },
+    /// Bridge an MCP stdio client to the gateway's `/v1/mcp` endpoint.
+    ///
+    /// Reads JSON-RPC messages from stdin, forwards them to a running gateway
+    /// with the API key in `HADRIAN_API_KEY`, and writes replies to stdout.
+    /// Point IDE or agent MCP configs that launch a command at this.
+    #[cfg(feature = "server")]
+    McpStdio {
+        /// MCP endpoint URL (e.g. `https://gateway.example.com/v1/mcp`).
+        /// Defaults to the host/port in the config file.
+        #[arg(long)]
+        url: Option<String>,
+    },
     /// Boot a one-off shell container for testing/debugging.
     ///
     /// Uses the configured `[features.shell]` runtime (microsandbox /
@@ -148,18 +197,60 @@ enum Command {
     },
 }
 
+#[cfg(feature = "server")]
+#[derive(clap::Subcommand, Debug)]
+enum ExportTarget {
+    /// Render Kubernetes manifests (ConfigMap, Secret, Deployment, Service)
+    /// or a values file for the Helm chart.
+    ///
+    /// The config file goes into the ConfigMap as written, apart from
+    /// listening on all interfaces and keeping SQLite data on a volume.
+    /// Variables it references as `${VAR}` become keys of a Secret loaded
+    /// into the pod's environment, with placeholder values unless
+    /// `--from-env` is given.
+    K8s {
+        /// Name of the Deployment and Service; other resources are prefixed
+        /// with it
+        #[arg(long, default_value = "hadrian")]
+        name: String,
+        /// Namespace to put the resources in (defaults to none, so `kubectl`
+        /// uses its current namespace)
+        #[arg(short, long)]
+        namespace: Option<String>,
+        /// Container image (defaults to the release matching this binary)
+        #[arg(long)]
+        image: Option<String>,
+        /// Number of replicas
+        #[arg(long, default_value = "1")]
+        replicas: u32,
+        /// Render a values file for the `helm/hadrian` chart instead of
+        /// plain manifests
+        #[arg(long)]
+        helm: bool,
+        /// Fill the Secret with the variables' values from the current
+        /// environment
+        #[arg(long)]
+        from_env: bool,
+        /// Directory to write one file per resource into (defaults to
+        /// printing a multi-document YAML stream to stdout)
+        #[arg(short, long)]
+        output_dir: Option<String>,
+    },
+}
+
 /// Dispatch to the appropriate subcommand handler.
 pub async fn dispatch(args: Args) {
     match args.command {
+        #[cfg(feature = "utoipa")]
+        Some(Command::Openapi { output, generate }) => match generate {
+            Some(language) => openapi::run_sdk_generate(language, output),
+            None => openapi::run_openapi_export(output),
+        },
+        #[cfg(not(feature = "utoipa"))]
         Some(Command::Openapi { output }) => {
-            #[cfg(feature = "utoipa")]
-            openapi::run_openapi_export(output);
-            #[cfg(not(feature = "utoipa"))]
-            {
-                let _ = output;
-                eprintln!("Error: OpenAPI export requires the 'utoipa' feature to be enabled");
-                std::process::exit(1);
-            }
+            let _ = output;
+            eprintln!("Error: OpenAPI export requires the 'utoipa' feature to be enabled");
+            std::process::exit(1);
         }
         Some(Command::Schema { output }) => {
             #[cfg(feature = "json-schema")]
@@ -208,11 +299,44 @@ pub async fn dispatch(args: Args) {
         Some(Command::Features) => {
             features::run_features();
         }
+        Some(Command::Grafana { output_dir }) => {
+            grafana::run_grafana_export(args.config.as_deref(), output_dir);
+        }
+        #[cfg(feature = "server")]
+        Some(Command::Export {
+            target:
+                ExportTarget::K8s {
+                    name,
+                    namespace,
+                    image,
+                    replicas,
+                    helm,
+                    from_env,
+                    output_dir,
+                },
+        }) => {
+            k8s::run_k8s_export(
+                args.config.as_deref(),
+                k8s::K8sExportOptions {
+                    name,
+                    namespace,
+                    image,
+                    replicas,
+                    helm,
+                    from_env,
+                    output_dir,
+                },
+            );
+        }
         #[cfg(feature = "server")]
         Some(Command::Healthcheck { url, timeout_secs }) => {
             healthcheck::run_healthcheck(args.config.as_deref(), url, timeout_secs).await;
         }
         #[cfg(feature = "server")]
+        Some(Command::McpStdio { url }) => {
+            mcp_stdio::run_mcp_stdio(args.config.as_deref(), url).await;
+        }
+        #[cfg(feature = "server")]
         Some(Command::Container {
             exec,
             file,
@@ -232,7 +356,10 @@ pub async fn dispatch(args: Args) {
             )
             .await;
         }
-        Some(Command::Serve) | None => {
+        Some(Command::Serve { check: true }) => {
+            check::run_check(args.config.as_deref()).await;
+        }
+        Some(Command::Serve { check: false }) | None => {
             server::run_server(args.config.as_deref(), args.no_browser).await;
         }
     }
@@ -312,30 +439,32 @@ pub fn default_data_dir() -> Option<PathBuf> {
 /// Resolve the config path, creating default config if necessary.
 /// Returns the config path and whether it was newly created.
 pub(crate) fn resolve_config_path(explicit_path: Option<&str>) -> Result<(PathBuf, bool), String> {
+    match find_config_path(explicit_path)? {
+        Some(path) => Ok((path, false)),
+        // No config found - create default config
+        None => create_default_config(),
+    }
+}
+
+/// Locate an existing config file without creating a default one.
+pub(crate) fn find_config_path(explicit_path: Option<&str>) -> Result<Option<PathBuf>, String> {
     // If explicit path is provided, use it
     if let Some(path) = explicit_path {
         let path = PathBuf::from(path);
         if !path.exists() {
             return Err(format!("Config file not found: {}", path.display()));
         }
-        return Ok((path, false));
+        return Ok(Some(path));
     }
 
     // Check for hadrian.toml in current directory
     let cwd_config = PathBuf::from("hadrian.toml");
     if cwd_config.exists() {
-        return Ok((cwd_config, false));
+        return Ok(Some(cwd_config));
     }
 
     // Check for config in default location
-    if let Some(default_path) = default_config_path()
-        && default_path.exists()
-    {
-        return Ok((default_path, false));
-    }
-
-    // No config found - create default config
-    create_default_config()
+    Ok(default_config_path().filter(|p| p.exists()))
 }
 
 /// Create the default configuration file and data directory.
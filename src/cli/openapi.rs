@@ -17,6 +17,27 @@ pub(crate) fn run_openapi_export(output: Option<String>) {
     }
 }
 
+/// Generate a typed client SDK for the endpoints compiled into this binary
+#[cfg(feature = "utoipa")]
+pub(crate) fn run_sdk_generate(language: crate::sdk::SdkLanguage, output: Option<String>) {
+    let mut spec = serde_json::to_value(crate::openapi::ApiDoc::build())
+        .expect("Failed to serialize OpenAPI spec to JSON");
+    crate::sdk::prepare_spec(&mut spec, &super::features::enabled_features());
+
+    let dir = std::path::PathBuf::from(output.unwrap_or_else(|| format!("hadrian-sdk-{language}")));
+    for file in crate::sdk::generate(language, &spec) {
+        let path = dir.join(&file.path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .unwrap_or_else(|e| panic!("Failed to create {}: {}", parent.display(), e));
+        }
+        std::fs::write(&path, &file.contents)
+            .unwrap_or_else(|e| panic!("Failed to write to {}: {}", path.display(), e));
+        eprintln!("Wrote {}", path.display());
+    }
+    eprintln!("{} SDK written to {}", language, dir.display());
+}
+
 /// Export JSON schema for the configuration file to file or stdout
 #[cfg(feature = "json-schema")]
 pub(crate) fn run_schema_export(output: Option<String>) {
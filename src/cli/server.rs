@@ -7,7 +7,7 @@ use crate::{
     app::{AppState, build_app},
     config, dlq,
     init::create_provider_instance,
-    jobs, observability, retention, usage_buffer, usage_sink,
+    jobs, observability, providers, retention, services, usage_buffer, usage_sink,
 };
 
 /// Open the UI in the system browser.
@@ -63,6 +63,7 @@ pub(crate) async fn run_server(explicit_config_path: Option<&str>, no_browser: b
     if let Err(e) = observability::metrics::init_metrics(&config.observability.metrics) {
         tracing::warn!(error = %e, "Failed to initialize metrics: {e}");
     }
+    observability::slo::init_slos(&config.observability.slos);
 
     tracing::info!(
         config_file = %config_path.display(),
@@ -106,26 +107,45 @@ pub(crate) async fn run_server(explicit_config_path: Option<&str>, no_browser: b
         );
     }
 
+    #[cfg(not(feature = "tls"))]
     if let Some(tls) = config.server.tls.as_ref() {
         if !tls.acknowledge_unsupported {
             tracing::error!(
-                "[server.tls] is set but the gateway does not yet terminate TLS \
-                 itself. Refusing to start to avoid serving the gateway on plain \
-                 HTTP while the operator believes TLS is active. Terminate TLS \
-                 upstream (reverse proxy / load balancer) and remove the \
-                 [server.tls] section, or set \
+                "[server.tls] is set but this binary was built without the `tls` \
+                 feature and cannot terminate TLS itself. Refusing to start to \
+                 avoid serving the gateway on plain HTTP while the operator \
+                 believes TLS is active. Rebuild with the `tls` feature, or \
+                 terminate TLS upstream (reverse proxy / load balancer) and \
+                 remove the [server.tls] section, or set \
                  `[server.tls].acknowledge_unsupported = true` to opt in to the \
-                 plaintext-listener behaviour while native TLS support is built out."
+                 plaintext-listener behaviour."
             );
             std::process::exit(1);
         }
         tracing::warn!(
             "[server.tls] is set with acknowledge_unsupported = true; the \
-             gateway will continue to listen on plain HTTP because native \
-             TLS is not yet implemented. Terminate TLS upstream."
+             gateway will continue to listen on plain HTTP because this binary \
+             was built without the `tls` feature. Terminate TLS upstream."
         );
     }
 
+    // Load certificates before any other startup work so a bad path or key
+    // fails fast instead of after migrations and provider warm-up.
+    #[cfg(feature = "tls")]
+    let tls_acceptor = match config.server.tls.as_ref() {
+        Some(tls) => match super::tls::acceptor(tls) {
+            Ok(acceptor) => Some((
+                acceptor,
+                std::time::Duration::from_secs(tls.handshake_timeout_secs),
+            )),
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to configure TLS");
+                std::process::exit(1);
+            }
+        },
+        None => None,
+    };
+
     let state = match AppState::new(config.clone()).await {
         Ok(state) => state,
         Err(e) => {
@@ -156,7 +176,7 @@ pub(crate) async fn run_server(explicit_config_path: Option<&str>, no_browser: b
         }
     }
 
-    // Start DLQ retry worker if configured
+    // Start DLQ retry and redrive workers if configured
     if let (Some(dlq), Some(db), Some(dlq_config)) = (
         state.dlq.clone(),
         state.db.clone(),
@@ -164,9 +184,29 @@ pub(crate) async fn run_server(explicit_config_path: Option<&str>, no_browser: b
     ) {
         let retry_config = dlq_config.retry().clone();
         let ttl_secs = dlq_config.ttl_secs();
+        let scheduler = state.job_scheduler.clone();
+
+        let redrive_policies = dlq_config.redrive_policies().to_vec();
+        if !redrive_policies.is_empty() {
+            let dlq = dlq.clone();
+            let db = db.clone();
+            let scheduler = scheduler.clone();
+            tokio::spawn(async move {
+                dlq::start_dlq_redrive_worker(dlq, db, redrive_policies, scheduler).await;
+            });
+        }
+
+        let failure_alerts = state
+            .email_notifier
+            .clone()
+            .filter(|_| config.notifications.dlq_failures.enabled)
+            .map(|notifier| dlq::DlqFailureAlerts {
+                notifier,
+                to: config.notifications.dlq_failures.to.clone(),
+            });
 
         tokio::spawn(async move {
-            dlq::start_dlq_worker(dlq, db, retry_config, ttl_secs).await;
+            dlq::start_dlq_worker(dlq, db, retry_config, ttl_secs, scheduler, failure_alerts).await;
         });
     }
 
@@ -244,8 +284,9 @@ pub(crate) async fn run_server(explicit_config_path: Option<&str>, no_browser: b
     // Start retention worker if configured and database is available
     if let Some(db) = state.db.clone() {
         let retention_config = config.retention.clone();
+        let scheduler = state.job_scheduler.clone();
         tokio::spawn(async move {
-            retention::start_retention_worker(db, retention_config).await;
+            retention::start_retention_worker(db, retention_config, scheduler).await;
         });
     }
 
@@ -260,6 +301,16 @@ pub(crate) async fn run_server(explicit_config_path: Option<&str>, no_browser: b
         });
     }
 
+    // Expired idempotency claims are only ignored, not removed, by new
+    // claims; without a database they expire from the cache on their own.
+    if let Some(db) = state.db.clone()
+        && state.idempotency.is_some()
+    {
+        tokio::spawn(async move {
+            jobs::start_idempotency_cleanup_worker(db).await;
+        });
+    }
+
     // The shutdown token lives for the whole server lifetime and gets
     // cancelled when the OS sends SIGTERM/SIGINT. Created here so the
     // responses workers below can subscribe — without this, the
@@ -328,10 +379,17 @@ pub(crate) async fn run_server(explicit_config_path: Option<&str>, no_browser: b
             .as_ref()
             .map(|fs| fs.vector_store());
         let file_storage = state.services.as_ref().map(|s| s.files.storage());
+        let scheduler = state.job_scheduler.clone();
 
         tokio::spawn(async move {
-            jobs::start_vector_store_cleanup_worker(db, vector_store, file_storage, cleanup_config)
-                .await;
+            jobs::start_vector_store_cleanup_worker(
+                db,
+                vector_store,
+                file_storage,
+                cleanup_config,
+                scheduler,
+            )
+            .await;
         });
     }
 
@@ -347,14 +405,256 @@ pub(crate) async fn run_server(explicit_config_path: Option<&str>, no_browser: b
         });
     }
 
+    // Start the scheduled reports worker. Generates per-org reports for
+    // each closed period and delivers them by email or webhook, recording
+    // every attempt in `scheduled_report_runs`.
+    if let Some(db) = state.db.clone()
+        && config.features.scheduled_reports.enabled
+    {
+        let service = services::ScheduledReportService::new(db.clone(), state.http_client.clone());
+        #[cfg(feature = "smtp")]
+        let service = match config
+            .notifications
+            .email
+            .as_ref()
+            .map(services::EmailSender::new)
+        {
+            Some(Ok(sender)) => service.with_email(sender),
+            Some(Err(e)) => {
+                tracing::error!(
+                    error = %e,
+                    "Failed to configure SMTP; scheduled email reports will fail"
+                );
+                service
+            }
+            None => service,
+        };
+        let reports_config = config.features.scheduled_reports.clone();
+        let cancel = shutdown_token.clone();
+        state.task_tracker.spawn(async move {
+            jobs::start_scheduled_reports_worker(service, db, reports_config, cancel).await;
+        });
+    }
+
+    // Start the access review reminder worker. Emails each organization's
+    // admins its stale users and API keys every `interval_days`.
+    if let (Some(db), Some(notifier)) = (state.db.clone(), state.email_notifier.clone())
+        && config.notifications.access_review_reminders.enabled
+    {
+        let reminders_config = config.notifications.access_review_reminders.clone();
+        let cancel = shutdown_token.clone();
+        state.task_tracker.spawn(async move {
+            jobs::start_access_review_reminders_worker(notifier, db, reminders_config, cancel)
+                .await;
+        });
+    }
+
+    // Start the elevation expiry worker. Restores members' previous roles
+    // once their role elevations expire.
+    if let Some(db) = state.db.clone()
+        && config.features.elevations.enabled
+    {
+        let elevations_config = config.features.elevations.clone();
+        let cancel = shutdown_token.clone();
+        state.task_tracker.spawn(async move {
+            jobs::start_elevation_expiry_worker(db, elevations_config, cancel).await;
+        });
+    }
+
+    // Start the scheduled tasks worker. Runs organizations' recurring prompts
+    // when they're due and records every run in `scheduled_task_runs`.
+    if state.db.is_some() && config.features.scheduled_tasks.enabled {
+        let worker_state = state.clone();
+        let cancel = shutdown_token.clone();
+        state.task_tracker.spawn(async move {
+            jobs::start_scheduled_tasks_worker(worker_state, cancel).await;
+        });
+    }
+
+    // Start the anomaly detection worker. Compares recent per-key and per-org
+    // usage with a rolling baseline and records (and optionally rate limits)
+    // spend spikes, model mix shifts and new referers.
+    if let Some(db) = state.db.clone()
+        && config.features.anomaly_detection.enabled
+    {
+        let anomaly_config = config.features.anomaly_detection.clone();
+        let cache = state.cache.clone();
+        let event_bus = state.event_bus.clone();
+        let cancel = shutdown_token.clone();
+        state.task_tracker.spawn(async move {
+            jobs::start_anomaly_detection_worker(db, cache, event_bus, anomaly_config, cancel)
+                .await;
+        });
+    }
+
+    // Start the vector store sync worker. Mirrors configured S3, Git and
+    // Confluence sources into vector stores, ingesting only documents whose
+    // version changed since the last run.
+    if let (Some(db), Some(svc)) = (state.db.clone(), state.services.as_ref())
+        && config.features.vector_store_sync.enabled
+    {
+        let sync_config = config.features.vector_store_sync.clone();
+        let service = services::SourceSyncService::new(
+            db.clone(),
+            svc.files.clone(),
+            svc.vector_stores.clone(),
+            state.http_client.clone(),
+            sync_config.max_file_bytes,
+        );
+        #[cfg(any(
+            feature = "document-extraction-basic",
+            feature = "document-extraction-full"
+        ))]
+        let service = match state.document_processor.clone() {
+            Some(processor) => service.with_document_processor(processor),
+            None => service,
+        };
+        let cancel = shutdown_token.clone();
+        state.task_tracker.spawn(async move {
+            jobs::start_vector_store_sync_worker(service, db, sync_config, cancel).await;
+        });
+    }
+
+    // Generate conversation titles and rolling summaries with a cheap model.
+    if let (Some(db), Some(svc)) = (state.db.clone(), state.services.as_ref())
+        && config.features.conversation_summaries.enabled
+    {
+        let summaries_config = config.features.conversation_summaries.clone();
+        // Config validation guarantees the provider exists.
+        if let Some(provider_config) = config.providers.get(&summaries_config.provider) {
+            match create_provider_instance(
+                provider_config,
+                &summaries_config.provider,
+                &state.circuit_breakers,
+            ) {
+                Ok(provider) => {
+                    let summarizer = services::ConversationSummarizer::new(
+                        provider,
+                        state.http_client.clone(),
+                        &summaries_config,
+                    );
+                    let conversations = svc.conversations.clone();
+                    let cancel = shutdown_token.clone();
+                    state.task_tracker.spawn(async move {
+                        jobs::start_conversation_summaries_worker(
+                            summarizer,
+                            conversations,
+                            db,
+                            summaries_config,
+                            cancel,
+                        )
+                        .await;
+                    });
+                }
+                Err(e) => {
+                    tracing::error!(
+                        provider = %summaries_config.provider,
+                        error = %e,
+                        "Failed to create provider for conversation summaries"
+                    );
+                }
+            }
+        }
+    }
+
+    // Push usage and provider health to the federation hub when this
+    // gateway is configured as a satellite.
+    if let (Some(db), Some(satellite)) = (state.db.clone(), config.federation.satellite.clone()) {
+        let http_client = state.http_client.clone();
+        let provider_health = state.provider_health.clone();
+        let circuit_breakers = state.circuit_breakers.clone();
+        let cancel = shutdown_token.clone();
+        state.task_tracker.spawn(async move {
+            jobs::start_federation_reporter_worker(
+                db,
+                http_client,
+                satellite,
+                provider_health,
+                circuit_breakers,
+                cancel,
+            )
+            .await;
+        });
+    }
+
+    // Share circuit breaker opens between replicas for providers with
+    // `mode = "shared"`. Breakers for dynamic providers are created lazily,
+    // so the worker runs whenever a cache is available.
+    if let Some(cache) = state.cache.clone() {
+        let circuit_breakers = state.circuit_breakers.clone();
+        let cancel = shutdown_token.clone();
+        state.task_tracker.spawn(async move {
+            jobs::start_circuit_breaker_sync_worker(cache, circuit_breakers, cancel).await;
+        });
+    } else if config
+        .providers
+        .iter()
+        .any(|(_, p)| p.circuit_breaker_config().mode == crate::config::CircuitBreakerMode::Shared)
+    {
+        tracing::warn!(
+            "Circuit breakers are configured with mode = \"shared\" but no cache is configured; \
+             circuit state will not be shared between nodes"
+        );
+    }
+
+    // Pick up provider settings changed through another node's admin API
+    if let Some(db) = state.db.clone() {
+        let settings = state.provider_settings.clone();
+        let cancel = shutdown_token.clone();
+        state.task_tracker.spawn(async move {
+            jobs::start_provider_settings_sync_worker(db, settings, cancel).await;
+        });
+    }
+
+    // Complete fine-tuning jobs that finish without a client reading them
+    if state.db.is_some() {
+        let worker_state = state.clone();
+        let cancel = shutdown_token.clone();
+        state.task_tracker.spawn(async move {
+            jobs::start_fine_tuning_sync_worker(worker_state, cancel).await;
+        });
+    }
+
+    // Pick up custom model catalog entries changed through another node's admin API
+    if let Some(db) = state.db.clone() {
+        let registry = state.model_catalog.clone();
+        let cancel = shutdown_token.clone();
+        state.task_tracker.spawn(async move {
+            jobs::start_model_catalog_entries_sync_worker(db, registry, cancel).await;
+        });
+    }
+
+    // Send reads to the primary while the Postgres read replica is down or lagging
+    #[cfg(feature = "database-postgres")]
+    if let (Some(db), config::DatabaseConfig::Postgres(pg)) = (state.db.clone(), &config.database)
+        && pg.read_url.is_some()
+    {
+        let max_lag_secs = pg.replica_max_lag_secs;
+        let interval = std::time::Duration::from_secs(pg.replica_check_interval_secs);
+        let cancel = shutdown_token.clone();
+        state.task_tracker.spawn(async move {
+            jobs::start_replica_monitor(db, max_lag_secs, interval, cancel).await;
+        });
+    }
+
+    // Keep SLO gauges current as their windows slide
+    if let Some(tracker) = observability::slo::tracker() {
+        let cancel = shutdown_token.clone();
+        state.task_tracker.spawn(async move {
+            jobs::start_slo_metrics_worker(tracker, cancel).await;
+        });
+    }
+
     // Start model catalog sync worker if enabled
     {
         let catalog_config = config.features.model_catalog.clone();
         let registry = state.model_catalog.clone();
         let http_client = state.http_client.clone();
+        let scheduler = state.job_scheduler.clone();
 
         tokio::spawn(async move {
-            jobs::start_model_catalog_sync_worker(registry, catalog_config, http_client).await;
+            jobs::start_model_catalog_sync_worker(registry, catalog_config, http_client, scheduler)
+                .await;
         });
     }
 
@@ -366,18 +666,46 @@ pub(crate) async fn run_server(explicit_config_path: Option<&str>, no_browser: b
             state.circuit_breakers.clone(),
             state.provider_health.clone(),
         );
+        // Probe results are only stored when there's a database
+        if let Some(db) = state.db.clone() {
+            health_checker = health_checker.with_database(db);
+        }
 
         // Register providers with health checks enabled
         for (name, provider_config) in config.providers.iter() {
             let health_config = provider_config.health_check_config();
-            if health_config.enabled {
-                match create_provider_instance(provider_config, name, &state.circuit_breakers) {
+            if !health_config.enabled {
+                continue;
+            }
+
+            // Providers with several endpoints are checked per endpoint, under
+            // the same `<provider>/<endpoint>` key as the endpoint's breaker
+            let endpoint_names = provider_config.endpoint_names();
+            let targets: Vec<(String, config::ProviderConfig)> = if endpoint_names.is_empty() {
+                vec![(name.clone(), provider_config.clone())]
+            } else {
+                endpoint_names
+                    .into_iter()
+                    .filter_map(|endpoint| {
+                        state.circuit_breakers.get_or_create_endpoint(
+                            name,
+                            endpoint,
+                            provider_config.circuit_breaker_config(),
+                        );
+                        let config = provider_config.for_endpoint(endpoint)?;
+                        Some((providers::registry::endpoint_key(name, endpoint), config))
+                    })
+                    .collect()
+            };
+
+            for (target, target_config) in targets {
+                match create_provider_instance(&target_config, &target, &state.circuit_breakers) {
                     Ok(provider) => {
-                        health_checker.register(name, provider, health_config.clone());
+                        health_checker.register(&target, provider, health_config.clone());
                     }
                     Err(e) => {
                         tracing::warn!(
-                            provider = %name,
+                            provider = %target,
                             error = %e,
                             "Failed to create provider for health checking"
                         );
@@ -482,6 +810,7 @@ pub(crate) async fn run_server(explicit_config_path: Option<&str>, no_browser: b
         None
     };
     let response_event_buffer = state.response_event_buffer.clone();
+    let drain = state.drain.clone();
     let app = build_app(&config, state);
 
     let bind_addr = format!("{}:{}", config.server.host, config.server.port);
@@ -493,7 +822,27 @@ pub(crate) async fn run_server(explicit_config_path: Option<&str>, no_browser: b
         }
     };
 
-    tracing::info!("Server listening on http://{}", bind_addr);
+    let scheme = if config.server.tls.is_some() && cfg!(feature = "tls") {
+        "https"
+    } else {
+        "http"
+    };
+    tracing::info!("Server listening on {}://{}", scheme, bind_addr);
+
+    // The gRPC listener forwards into a clone of the same router, so it ends
+    // with the shutdown token rather than the HTTP listener's graceful shutdown.
+    #[cfg(feature = "grpc")]
+    if let Some(grpc) = &config.server.grpc {
+        let addr = std::net::SocketAddr::new(grpc.host.unwrap_or(config.server.host), grpc.port);
+        let app = app.clone();
+        let cancel = shutdown_token.clone();
+        task_tracker.spawn(async move {
+            tracing::info!("gRPC server listening on {}", addr);
+            if let Err(e) = super::grpc::serve(addr, app, cancel.cancelled_owned()).await {
+                tracing::error!(error = %e, bind_addr = %addr, "gRPC server error");
+            }
+        });
+    }
 
     // Warm the static models cache on a background task. With many providers
     // (including slow/dead ones holding open connections until they time out)
@@ -545,15 +894,51 @@ pub(crate) async fn run_server(explicit_config_path: Option<&str>, no_browser: b
     // `into_make_service_with_connect_info` is required so middleware can read
     // the connecting peer address via `ConnectInfo<SocketAddr>` for IP-based
     // rate limits, API-key IP allowlists, and audit logging.
+    //
+    // With native TLS the accept loop in `cli::tls` inserts `ConnectInfo`
+    // itself, along with the verified client certificate (if any).
+    //
+    // Before the listener stops, the instance drains: readiness fails and new
+    // data-plane requests get a 503 while in-flight ones (including streams)
+    // get up to `drain_timeout_secs` to finish. A drain started earlier
+    // through the admin API is simply continued.
     let shutdown_token_signal = shutdown_token.clone();
+    let drain_timeout = std::time::Duration::from_secs(shutdown_config.drain_timeout_secs);
+    let shutdown_signal = async move {
+        wait_for_shutdown_signal().await;
+        drain.start();
+        if drain.wait_idle(drain_timeout).await {
+            tracing::info!("All in-flight requests completed");
+        } else {
+            tracing::warn!(
+                in_flight = drain.in_flight(),
+                timeout_secs = drain_timeout.as_secs(),
+                "Drain timeout reached with requests still in flight"
+            );
+        }
+        shutdown_token_signal.cancel();
+    };
+    #[cfg(feature = "tls")]
+    let serve_result = match tls_acceptor {
+        Some((acceptor, handshake_timeout)) => {
+            super::tls::serve(listener, app, acceptor, handshake_timeout, shutdown_signal).await;
+            Ok(())
+        }
+        None => {
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+            )
+            .with_graceful_shutdown(shutdown_signal)
+            .await
+        }
+    };
+    #[cfg(not(feature = "tls"))]
     let serve_result = axum::serve(
         listener,
         app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
     )
-    .with_graceful_shutdown(async move {
-        wait_for_shutdown_signal().await;
-        shutdown_token_signal.cancel();
-    })
+    .with_graceful_shutdown(shutdown_signal)
     .await;
 
     if let Err(e) = serve_result {
@@ -0,0 +1,167 @@
+//! Native TLS listener with optional client certificate verification.
+//!
+//! `axum::serve` only accepts plain TCP, so when `[server.tls]` is set the
+//! accept loop lives here: each connection is handshaken with rustls, then
+//! served by hyper with the peer address and (if presented) the verified
+//! client certificate inserted into every request's extensions. The API
+//! middleware reads the certificate to authenticate mapped service accounts.
+
+use std::{future::Future, net::SocketAddr, sync::Arc, time::Duration};
+
+use axum::{Router, extract::ConnectInfo};
+use hyper::{body::Incoming, service::service_fn};
+use hyper_util::{
+    rt::{TokioExecutor, TokioIo},
+    server::{conn::auto::Builder, graceful::GracefulShutdown},
+};
+use rustls::{
+    RootCertStore, ServerConfig,
+    pki_types::{CertificateDer, PrivateKeyDer, pem::PemObject},
+    server::WebPkiClientVerifier,
+};
+use tokio::net::TcpListener;
+use tokio_rustls::TlsAcceptor;
+use tower::ServiceExt;
+
+use crate::{
+    auth::ClientCertificate,
+    config::{ClientCertMode, TlsConfig},
+};
+
+/// Build a TLS acceptor from `[server.tls]`, loading the certificate chain,
+/// private key and (for mTLS) the client CA bundle.
+pub(super) fn acceptor(tls: &TlsConfig) -> Result<TlsAcceptor, String> {
+    // Both ring and aws-lc-rs end up in the dependency graph, so rustls can't
+    // pick a process default; pass the provider explicitly.
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+
+    let certs = load_certs(&tls.cert_path)?;
+    let key = PrivateKeyDer::from_pem_file(&tls.key_path)
+        .map_err(|e| format!("failed to read private key {}: {e}", tls.key_path))?;
+
+    let builder = ServerConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .map_err(|e| format!("invalid TLS protocol configuration: {e}"))?;
+
+    let builder = match &tls.client_auth {
+        Some(client_auth) => {
+            let mut roots = RootCertStore::empty();
+            for cert in load_certs(&client_auth.ca_path)? {
+                roots.add(cert).map_err(|e| {
+                    format!(
+                        "invalid client CA certificate in {}: {e}",
+                        client_auth.ca_path
+                    )
+                })?;
+            }
+            let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider);
+            let verifier = match client_auth.mode {
+                ClientCertMode::Optional => verifier.allow_unauthenticated(),
+                ClientCertMode::Required => verifier,
+            };
+            let verifier = verifier
+                .build()
+                .map_err(|e| format!("failed to build client certificate verifier: {e}"))?;
+            builder.with_client_cert_verifier(verifier)
+        }
+        None => builder.with_no_client_auth(),
+    };
+
+    let mut config = builder
+        .with_single_cert(certs, key)
+        .map_err(|e| format!("invalid server certificate or key: {e}"))?;
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+    Ok(TlsAcceptor::from(Arc::new(config)))
+}
+
+fn load_certs(path: &str) -> Result<Vec<CertificateDer<'static>>, String> {
+    let certs = CertificateDer::pem_file_iter(path)
+        .and_then(|iter| iter.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| format!("failed to read certificates from {path}: {e}"))?;
+    if certs.is_empty() {
+        return Err(format!("no certificates found in {path}"));
+    }
+    Ok(certs)
+}
+
+/// Accept TLS connections until `shutdown` resolves, then wait for in-flight
+/// connections to finish.
+pub(super) async fn serve(
+    listener: TcpListener,
+    app: Router,
+    acceptor: TlsAcceptor,
+    handshake_timeout: Duration,
+    shutdown: impl Future<Output = ()>,
+) {
+    let builder = Builder::new(TokioExecutor::new());
+    let graceful = GracefulShutdown::new();
+    tokio::pin!(shutdown);
+
+    loop {
+        let (stream, remote_addr) = tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok(conn) => conn,
+                Err(e) => {
+                    // Typically EMFILE; back off instead of spinning
+                    tracing::warn!(error = %e, "Failed to accept connection");
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    continue;
+                }
+            },
+            _ = &mut shutdown => break,
+        };
+
+        let acceptor = acceptor.clone();
+        let app = app.clone();
+        let builder = builder.clone();
+        let watcher = graceful.watcher();
+
+        tokio::spawn(async move {
+            let tls_stream =
+                match tokio::time::timeout(handshake_timeout, acceptor.accept(stream)).await {
+                    Ok(Ok(stream)) => stream,
+                    Ok(Err(e)) => {
+                        tracing::debug!(%remote_addr, error = %e, "TLS handshake failed");
+                        return;
+                    }
+                    Err(_) => {
+                        tracing::debug!(%remote_addr, "TLS handshake timed out");
+                        return;
+                    }
+                };
+
+            let client_cert = peer_certificate(&tls_stream, remote_addr);
+            let service = service_fn(move |mut req: axum::http::Request<Incoming>| {
+                req.extensions_mut().insert(ConnectInfo(remote_addr));
+                if let Some(cert) = &client_cert {
+                    req.extensions_mut().insert(cert.clone());
+                }
+                app.clone().oneshot(req)
+            });
+
+            let conn = builder.serve_connection_with_upgrades(TokioIo::new(tls_stream), service);
+            if let Err(e) = watcher.watch(conn).await {
+                tracing::debug!(%remote_addr, error = %e, "Connection error");
+            }
+        });
+    }
+
+    drop(listener);
+    graceful.shutdown().await;
+}
+
+/// Parse the verified leaf certificate presented by the client, if any.
+fn peer_certificate(
+    stream: &tokio_rustls::server::TlsStream<tokio::net::TcpStream>,
+    remote_addr: SocketAddr,
+) -> Option<Arc<ClientCertificate>> {
+    let der = stream.get_ref().1.peer_certificates()?.first()?;
+    match ClientCertificate::from_der(der) {
+        Ok(cert) => Some(Arc::new(cert)),
+        Err(e) => {
+            tracing::warn!(%remote_addr, error = %e, "Ignoring unparseable client certificate");
+            None
+        }
+    }
+}
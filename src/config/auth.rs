@@ -43,6 +43,19 @@ pub struct AuthConfig {
     #[serde(default)]
     pub session: Option<SessionConfig>,
 
+    /// Externally-issued JWTs accepted as bearer credentials on `/api` routes,
+    /// as an alternative to gateway API keys. Works in every auth mode.
+    ///
+    /// ```toml
+    /// [[auth.jwt]]
+    /// issuer = "https://auth.example.com/"
+    /// audience = "hadrian"
+    /// jwks_url = "https://auth.example.com/.well-known/jwks.json"
+    /// org_claim = "https://example.com/claims.org"
+    /// ```
+    #[serde(default)]
+    pub jwt: Vec<BearerJwtConfig>,
+
     /// Authorization (RBAC) configuration.
     #[serde(default)]
     pub rbac: RbacConfig,
@@ -62,6 +75,18 @@ pub struct AuthConfig {
     /// callback domain, with optional allow/deny lists.
     #[serde(default)]
     pub oauth_pkce: OAuthPkceConfig,
+
+    /// Step-up authentication for high-risk admin operations.
+    #[serde(default)]
+    pub step_up: StepUpConfig,
+
+    /// Admin UI sign-in options (passkeys).
+    #[serde(default)]
+    pub admin: AdminAuthConfig,
+
+    /// Exchange of API keys for short-lived, narrowly-scoped tokens.
+    #[serde(default)]
+    pub token_exchange: TokenExchangeConfig,
 }
 
 impl AuthConfig {
@@ -74,6 +99,7 @@ impl AuthConfig {
             session.validate()?;
         }
         self.mode.validate()?;
+        self.validate_bearer_jwt()?;
         self.rbac.validate()?;
         if let Some(ref bootstrap) = self.bootstrap {
             bootstrap.validate()?;
@@ -82,6 +108,42 @@ impl AuthConfig {
             emergency.validate()?;
         }
         self.oauth_pkce.validate()?;
+        self.step_up.validate()?;
+        self.admin.validate()?;
+        self.token_exchange.validate()?;
+        if self.token_exchange.enabled
+            && self
+                .jwt
+                .iter()
+                .any(|jwt| jwt.issuer == self.token_exchange.issuer)
+        {
+            return Err(ConfigError::Validation(format!(
+                "auth.token_exchange.issuer '{}' is also configured in auth.jwt",
+                self.token_exchange.issuer
+            )));
+        }
+        Ok(())
+    }
+
+    fn validate_bearer_jwt(&self) -> Result<(), ConfigError> {
+        if self.jwt.is_empty() {
+            return Ok(());
+        }
+        if !cfg!(feature = "jwt") {
+            return Err(ConfigError::Validation(
+                "auth.jwt requires the 'jwt' feature to be enabled".into(),
+            ));
+        }
+        let mut issuers = std::collections::HashSet::new();
+        for jwt in &self.jwt {
+            jwt.validate()?;
+            if !issuers.insert(jwt.issuer.as_str()) {
+                return Err(ConfigError::Validation(format!(
+                    "auth.jwt: issuer '{}' is configured more than once",
+                    jwt.issuer
+                )));
+            }
+        }
         Ok(())
     }
 
@@ -662,7 +724,7 @@ fn validate_jwt_audience(field: &str, audience: &OneOrMany<String>) -> Result<()
 }
 
 /// API key authentication configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(deny_unknown_fields)]
 pub struct ApiKeyAuthConfig {
@@ -679,10 +741,25 @@ pub struct ApiKeyAuthConfig {
     #[serde(default)]
     pub generation_prefix: Option<String>,
 
-    /// Hash algorithm for storing keys.
+    /// Hash algorithm for storing keys. Keys stored under another scheme are
+    /// re-hashed to this one the next time they are used.
     #[serde(default)]
     pub hash_algorithm: HashAlgorithm,
 
+    /// Server-side secret mixed into key hashes (HMAC-SHA256), so a leaked
+    /// database alone can't be used to test candidate keys. Required for
+    /// `hmac_sha256`; optional for `argon2`. Keep it out of the database,
+    /// e.g. `pepper = "${API_KEY_PEPPER}"`. Changing it invalidates every
+    /// key hashed with it.
+    #[serde(default)]
+    pub pepper: Option<String>,
+
+    /// Accept keys still stored under the legacy unpeppered SHA-256 scheme
+    /// and migrate them on use. Disable once all keys have been re-hashed to
+    /// skip the extra lookup for unknown keys.
+    #[serde(default = "default_true")]
+    pub legacy_fallback: bool,
+
     /// Cache API key lookups for this many seconds.
     /// Set to 0 to disable caching (every request hits the database).
     #[serde(default = "default_key_cache_ttl")]
@@ -696,11 +773,30 @@ impl Default for ApiKeyAuthConfig {
             key_prefix: default_api_key_prefix(),
             generation_prefix: None,
             hash_algorithm: HashAlgorithm::default(),
+            pepper: None,
+            legacy_fallback: true,
             cache_ttl_secs: default_key_cache_ttl(),
         }
     }
 }
 
+impl std::fmt::Debug for ApiKeyAuthConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApiKeyAuthConfig")
+            .field("header_name", &self.header_name)
+            .field("key_prefix", &self.key_prefix)
+            .field("generation_prefix", &self.generation_prefix)
+            .field("hash_algorithm", &self.hash_algorithm)
+            .field("pepper", &self.pepper.as_ref().map(|_| "****"))
+            .field("legacy_fallback", &self.legacy_fallback)
+            .field("cache_ttl_secs", &self.cache_ttl_secs)
+            .finish()
+    }
+}
+
+/// Minimum pepper length in bytes.
+const MIN_PEPPER_LEN: usize = 32;
+
 impl ApiKeyAuthConfig {
     fn validate(&self) -> Result<(), ConfigError> {
         if self.header_name.is_empty() {
@@ -708,6 +804,20 @@ impl ApiKeyAuthConfig {
                 "API key header name cannot be empty".into(),
             ));
         }
+        match &self.pepper {
+            Some(pepper) if pepper.len() < MIN_PEPPER_LEN => {
+                return Err(ConfigError::Validation(format!(
+                    "auth.api_key.pepper must be at least {MIN_PEPPER_LEN} bytes"
+                )));
+            }
+            None if self.hash_algorithm == HashAlgorithm::HmacSha256 => {
+                return Err(ConfigError::Validation(
+                    "auth.api_key.hash_algorithm = \"hmac_sha256\" requires auth.api_key.pepper"
+                        .into(),
+                ));
+            }
+            _ => {}
+        }
         Ok(())
     }
 
@@ -736,17 +846,49 @@ fn default_key_cache_ttl() -> u64 {
 }
 
 /// Hash algorithm for API keys.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+///
+/// The name is stored with each key (`api_keys.hash_scheme`) so keys hashed
+/// under an older scheme can still be found and re-hashed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum HashAlgorithm {
     /// SHA-256 (fast, suitable for high-entropy keys).
     #[default]
     Sha256,
-    /// Argon2id (slow, more secure if keys might be low-entropy).
+    /// HMAC-SHA256 keyed with `pepper` (fast; a database leak alone reveals
+    /// nothing without the pepper).
+    HmacSha256,
+    /// Argon2id (slow, more secure if keys might be low-entropy). Keys are
+    /// looked up by a fast digest (peppered if `pepper` is set) and then
+    /// verified against a salted Argon2id hash on uncached lookups.
+    #[serde(alias = "argon2id")]
     Argon2,
 }
 
+impl HashAlgorithm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HashAlgorithm::Sha256 => "sha256",
+            HashAlgorithm::HmacSha256 => "hmac_sha256",
+            HashAlgorithm::Argon2 => "argon2",
+        }
+    }
+}
+
+impl std::str::FromStr for HashAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sha256" => Ok(HashAlgorithm::Sha256),
+            "hmac_sha256" => Ok(HashAlgorithm::HmacSha256),
+            "argon2" | "argon2id" => Ok(HashAlgorithm::Argon2),
+            other => Err(format!("unknown API key hash scheme: {other}")),
+        }
+    }
+}
+
 /// JWT authentication configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
@@ -788,6 +930,120 @@ pub struct JwtAuthConfig {
     pub allowed_algorithms: Vec<JwtAlgorithm>,
 }
 
+/// An external issuer whose JWTs are accepted as data-plane bearer tokens.
+///
+/// Tokens are routed to an entry by their `iss` claim, verified against the
+/// issuer's JWKS (cached, and re-fetched when an unknown `kid` appears so key
+/// rotation is picked up without a restart), and then mapped onto gateway
+/// entities through the configured claim paths.
+///
+/// Claim paths are dot-separated (`tenant.org`) for nested objects. A claim
+/// whose name itself contains dots (common for namespaced claims such as
+/// `https://example.com/org`) is matched as-is before the path is split.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct BearerJwtConfig {
+    /// Expected issuer (`iss` claim).
+    pub issuer: String,
+
+    /// Expected audience (`aud` claim). Can be a single value or a list.
+    pub audience: OneOrMany<String>,
+
+    /// JWKS URL for fetching the issuer's public keys.
+    pub jwks_url: String,
+
+    /// How long fetched keys are trusted before the JWKS is re-fetched, in seconds.
+    #[serde(default = "default_jwks_refresh")]
+    pub jwks_refresh_secs: u64,
+
+    /// Allowed signing algorithms. Defaults to RS256, RS384, RS512, ES256, ES384.
+    #[serde(default = "default_allowed_algorithms")]
+    pub allowed_algorithms: Vec<JwtAlgorithm>,
+
+    /// Claim recorded as the caller's subject in usage logs.
+    #[serde(default = "default_identity_claim")]
+    pub subject_claim: String,
+
+    /// Claim matched against `users.external_id` as `<issuer>|<value>` to
+    /// attribute requests to a gateway user. Unmatched values leave the
+    /// request without a user; a matched user outside the token's
+    /// organization or project is rejected.
+    #[serde(default)]
+    pub user_claim: Option<String>,
+
+    /// Claim holding the organization slug or ID.
+    #[serde(default)]
+    pub org_claim: Option<String>,
+
+    /// Claim holding the project slug or ID within the organization.
+    /// Requires `org_claim`.
+    #[serde(default)]
+    pub project_claim: Option<String>,
+
+    /// Claim holding the caller's roles (a string or array of strings),
+    /// evaluated by RBAC policies like IdP roles.
+    #[serde(default)]
+    pub roles_claim: Option<String>,
+
+    /// Reject tokens whose organization claim is missing or doesn't resolve
+    /// to an existing organization.
+    #[serde(default)]
+    pub require_org: bool,
+}
+
+impl BearerJwtConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.issuer.trim().is_empty() {
+            return Err(ConfigError::Validation(
+                "auth.jwt.issuer cannot be empty".into(),
+            ));
+        }
+        if self.jwks_url.trim().is_empty() {
+            return Err(ConfigError::Validation(format!(
+                "auth.jwt.jwks_url cannot be empty (issuer '{}')",
+                self.issuer
+            )));
+        }
+        validate_jwt_audience("auth.jwt", &self.audience)?;
+        if self.allowed_algorithms.is_empty() {
+            return Err(ConfigError::Validation(format!(
+                "auth.jwt.allowed_algorithms cannot be empty (issuer '{}')",
+                self.issuer
+            )));
+        }
+        if self.project_claim.is_some() && self.org_claim.is_none() {
+            return Err(ConfigError::Validation(format!(
+                "auth.jwt.project_claim requires org_claim (issuer '{}')",
+                self.issuer
+            )));
+        }
+        if self.require_org && self.org_claim.is_none() {
+            return Err(ConfigError::Validation(format!(
+                "auth.jwt.require_org requires org_claim (issuer '{}')",
+                self.issuer
+            )));
+        }
+        Ok(())
+    }
+
+    /// Validator settings for this issuer.
+    #[cfg(feature = "jwt")]
+    pub fn validator_config(&self) -> JwtAuthConfig {
+        JwtAuthConfig {
+            issuer: self.issuer.clone(),
+            audience: self.audience.clone(),
+            jwks_url: self.jwks_url.clone(),
+            jwks_refresh_secs: self.jwks_refresh_secs,
+            identity_claim: self.subject_claim.clone(),
+            org_claim: None,
+            additional_claims: Vec::new(),
+            allow_expired: false,
+            allowed_algorithms: self.allowed_algorithms.clone(),
+        }
+    }
+}
+
 /// JWT signing algorithm.
 /// SECURITY: Asymmetric algorithms (RS*, ES*) are strongly recommended.
 /// HMAC algorithms (HS*) should only be used when you control both signing and verification.
@@ -1179,8 +1435,9 @@ pub struct EnhancedSessionConfig {
     pub track_devices: bool,
 
     /// Maximum concurrent sessions per user. 0 = unlimited.
-    /// When exceeded, oldest sessions are automatically invalidated.
-    /// Requires `enabled = true`. Enforcement is in Phase 2.
+    /// When exceeded on login, the oldest sessions are invalidated and each
+    /// eviction is recorded as an `auth.session.evicted` audit event.
+    /// Requires `enabled = true`.
     #[serde(default)]
     pub max_concurrent_sessions: u32,
 
@@ -2060,6 +2317,53 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[cfg(feature = "jwt")]
+    #[test]
+    fn test_bearer_jwt_config() {
+        let toml_str = r#"
+            [mode]
+            type = "api_key"
+
+            [[jwt]]
+            issuer = "https://auth.example.com/"
+            audience = "hadrian"
+            jwks_url = "https://auth.example.com/.well-known/jwks.json"
+            org_claim = "https://example.com/org"
+            project_claim = "tenant.project"
+        "#;
+        let mut config: AuthConfig = toml::from_str(toml_str).unwrap();
+        config.validate().unwrap();
+        let jwt = &config.jwt[0];
+        assert_eq!(jwt.subject_claim, "sub");
+        assert_eq!(jwt.jwks_refresh_secs, 3600);
+        assert!(!jwt.require_org);
+        assert_eq!(jwt.validator_config().issuer, "https://auth.example.com/");
+    }
+
+    #[test]
+    fn test_bearer_jwt_config_rejects_invalid() {
+        let base = r#"
+            [[jwt]]
+            issuer = "https://auth.example.com/"
+            audience = "hadrian"
+            jwks_url = "https://auth.example.com/.well-known/jwks.json"
+        "#;
+
+        // project_claim without org_claim
+        let mut config: AuthConfig =
+            toml::from_str(&format!("{base}project_claim = \"project\"")).unwrap();
+        assert!(config.validate().is_err());
+
+        // Duplicate issuer
+        let mut config: AuthConfig = toml::from_str(&format!("{base}{base}")).unwrap();
+        assert!(config.validate().is_err());
+
+        // Empty audience
+        let mut config: AuthConfig =
+            toml::from_str(&base.replace(r#""hadrian""#, r#""""#)).unwrap();
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_api_key_config_defaults() {
         let config = ApiKeyAuthConfig::default();
@@ -2126,6 +2430,135 @@ mod tests {
         assert!(config.is_callback_host_allowed("good.example.com"));
         assert!(!config.is_callback_host_allowed("bad.example.com"));
     }
+
+    #[test]
+    fn test_step_up_config() {
+        let config: AuthConfig = toml::from_str("").unwrap();
+        assert!(!config.step_up.enabled);
+        assert!(!config.step_up.routes.is_empty());
+
+        let toml_str = r#"
+            [step_up]
+            enabled = true
+            routes = ["DELETE /admin/v1/organizations/{org_slug}"]
+        "#;
+        let mut config: AuthConfig = toml::from_str(toml_str).unwrap();
+        config.validate().unwrap();
+        assert_eq!(config.step_up.routes.len(), 1);
+        assert_eq!(config.step_up.max_auth_age_secs, 900);
+
+        config.step_up.routes = vec!["/admin/v1/organizations".into()];
+        assert!(config.validate().is_err());
+    }
+
+    #[cfg(feature = "jwt")]
+    #[test]
+    fn test_token_exchange_config_validation() {
+        let mut config: AuthConfig = toml::from_str("").unwrap();
+        assert!(!config.token_exchange.enabled);
+        config.validate().unwrap();
+
+        let toml_str = r#"
+            [token_exchange]
+            enabled = true
+            signing_key = "short"
+        "#;
+        let mut config: AuthConfig = toml::from_str(toml_str).unwrap();
+        assert!(config.validate().is_err());
+
+        config.token_exchange.signing_key = Some("0123456789abcdef0123456789abcdef".into());
+        config.validate().unwrap();
+        assert_eq!(config.token_exchange.default_ttl_secs, 900);
+
+        config.token_exchange.default_ttl_secs = config.token_exchange.max_ttl_secs + 1;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_admin_passkeys_default_disabled() {
+        let config: AuthConfig = toml::from_str("").unwrap();
+        assert_eq!(config.admin.passkeys, PasskeyPolicy::Disabled);
+        assert!(config.admin.passkey_login);
+        config.admin.validate().unwrap();
+    }
+
+    #[test]
+    fn test_webauthn_config_validation() {
+        let mut webauthn = WebAuthnConfig {
+            rp_id: "example.com".into(),
+            rp_origin: "https://admin.example.com".into(),
+            rp_name: "Hadrian".into(),
+            challenge_ttl_secs: 300,
+        };
+        webauthn.validate().unwrap();
+
+        webauthn.rp_id = "other.com".into();
+        assert!(webauthn.validate().is_err());
+
+        webauthn.rp_id = "example.com".into();
+        webauthn.rp_origin = "http://admin.example.com".into();
+        assert!(webauthn.validate().is_err());
+
+        webauthn.rp_id = "localhost".into();
+        webauthn.rp_origin = "http://localhost:8080".into();
+        webauthn.validate().unwrap();
+    }
+
+    #[cfg(feature = "webauthn")]
+    #[test]
+    fn test_admin_passkeys_require_webauthn_settings() {
+        let toml_str = r#"
+            [admin]
+            passkeys = "required"
+        "#;
+        let config: AuthConfig = toml::from_str(toml_str).unwrap();
+        assert!(config.admin.validate().is_err());
+
+        let toml_str = r#"
+            [admin]
+            passkeys = "optional"
+            passkey_login = false
+
+            [admin.webauthn]
+            rp_id = "gateway.example.com"
+            rp_origin = "https://gateway.example.com"
+        "#;
+        let config: AuthConfig = toml::from_str(toml_str).unwrap();
+        config.admin.validate().unwrap();
+        assert!(!config.admin.passkey_login);
+        assert_eq!(config.admin.webauthn.unwrap().challenge_ttl_secs, 300);
+    }
+
+    #[test]
+    fn test_api_key_hash_config() {
+        let toml_str = r#"
+            [api_key]
+            hash_algorithm = "argon2id"
+        "#;
+        let config: AuthConfig = toml::from_str(toml_str).unwrap();
+        config.validate().unwrap();
+        let api_key = config.api_key_config();
+        assert_eq!(api_key.hash_algorithm, HashAlgorithm::Argon2);
+        assert!(api_key.legacy_fallback);
+
+        // HMAC needs a pepper, and a short one is rejected
+        let mut api_key = api_key.clone();
+        api_key.hash_algorithm = HashAlgorithm::HmacSha256;
+        assert!(api_key.validate().is_err());
+        api_key.pepper = Some("too-short".into());
+        assert!(api_key.validate().is_err());
+        api_key.pepper = Some("0123456789abcdef0123456789abcdef".into());
+        api_key.validate().unwrap();
+        assert!(!format!("{api_key:?}").contains("0123456789abcdef"));
+
+        for scheme in [
+            HashAlgorithm::Sha256,
+            HashAlgorithm::HmacSha256,
+            HashAlgorithm::Argon2,
+        ] {
+            assert_eq!(scheme.as_str().parse::<HashAlgorithm>(), Ok(scheme));
+        }
+    }
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -2279,3 +2712,401 @@ fn host_matches(host: &str, pattern: &str) -> bool {
     }
     host == pattern || host.ends_with(&format!(".{pattern}"))
 }
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Step-up Authentication Configuration
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Require recent re-authentication for high-risk admin operations.
+///
+/// A request to a protected route passes if the caller signed in within
+/// `max_auth_age_secs` (SSO session cookie), verified a TOTP code via
+/// `POST /admin/v1/me/step-up/verify` within `verification_ttl_secs`, or
+/// sends a current code in the `X-Step-Up-TOTP` header. Otherwise it is
+/// rejected with `403 step_up_required`.
+///
+/// ```toml
+/// [auth.step_up]
+/// enabled = true
+/// max_auth_age_secs = 900
+/// routes = ["DELETE /admin/v1/organizations/{slug}"]
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct StepUpConfig {
+    /// Enforce step-up authentication on `routes`. TOTP enrollment endpoints
+    /// are available either way.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// How recently an SSO session must have been created to count as a
+    /// fresh sign-in, in seconds. Set to 0 to always require TOTP.
+    #[serde(default = "default_step_up_max_auth_age")]
+    pub max_auth_age_secs: u64,
+
+    /// How long a successful TOTP verification satisfies step-up, in seconds.
+    /// Requires a cache; without one, codes must be sent per request in the
+    /// `X-Step-Up-TOTP` header.
+    #[serde(default = "default_step_up_verification_ttl")]
+    pub verification_ttl_secs: u64,
+
+    /// Issuer label shown in authenticator apps.
+    #[serde(default = "default_totp_issuer")]
+    pub totp_issuer: String,
+
+    /// Failed TOTP attempts allowed per user within `verification_ttl_secs`
+    /// before further attempts are rejected. Without a cache, failures are
+    /// counted per gateway instance.
+    #[serde(default = "default_step_up_max_failures")]
+    pub max_failed_attempts: u32,
+
+    /// Protected routes as `"METHOD /admin/v1/path"` patterns. `{name}`
+    /// segments match any single path segment. Defaults to organization
+    /// deletion, SSO and SCIM configuration changes, SCIM token rotation, and
+    /// removing a TOTP authenticator.
+    #[serde(default = "default_step_up_routes")]
+    pub routes: Vec<String>,
+}
+
+impl Default for StepUpConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_auth_age_secs: default_step_up_max_auth_age(),
+            verification_ttl_secs: default_step_up_verification_ttl(),
+            totp_issuer: default_totp_issuer(),
+            max_failed_attempts: default_step_up_max_failures(),
+            routes: default_step_up_routes(),
+        }
+    }
+}
+
+fn default_step_up_max_auth_age() -> u64 {
+    900
+}
+
+fn default_step_up_verification_ttl() -> u64 {
+    600
+}
+
+fn default_totp_issuer() -> String {
+    "Hadrian".to_string()
+}
+
+fn default_step_up_max_failures() -> u32 {
+    5
+}
+
+fn default_step_up_routes() -> Vec<String> {
+    [
+        "DELETE /admin/v1/organizations/{org_slug}",
+        "POST /admin/v1/organizations/{org_slug}/sso-config",
+        "PATCH /admin/v1/organizations/{org_slug}/sso-config",
+        "DELETE /admin/v1/organizations/{org_slug}/sso-config",
+        "POST /admin/v1/organizations/{org_slug}/scim-config",
+        "PATCH /admin/v1/organizations/{org_slug}/scim-config",
+        "DELETE /admin/v1/organizations/{org_slug}/scim-config",
+        "POST /admin/v1/organizations/{org_slug}/scim-config/rotate-token",
+        "DELETE /admin/v1/me/step-up/totp",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+impl StepUpConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.verification_ttl_secs == 0 {
+            return Err(ConfigError::Validation(
+                "auth.step_up.verification_ttl_secs must be greater than 0".into(),
+            ));
+        }
+        if self.totp_issuer.trim().is_empty() || self.totp_issuer.contains(':') {
+            return Err(ConfigError::Validation(
+                "auth.step_up.totp_issuer must be non-empty and must not contain ':'".into(),
+            ));
+        }
+        for route in &self.routes {
+            let valid = route.split_once(' ').is_some_and(|(method, path)| {
+                http::Method::from_bytes(method.as_bytes()).is_ok() && path.starts_with('/')
+            });
+            if !valid {
+                return Err(ConfigError::Validation(format!(
+                    "auth.step_up.routes entry '{route}' must look like \"DELETE /admin/v1/...\""
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Admin Sign-in Configuration
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Admin UI sign-in options.
+///
+/// Passkeys (WebAuthn credentials) are registered per user at
+/// `/auth/webauthn/register` and used at `/auth/webauthn/authenticate`,
+/// either to sign in without SSO or to verify an existing SSO session.
+///
+/// ```toml
+/// [auth.admin]
+/// passkeys = "required"
+///
+/// [auth.admin.webauthn]
+/// rp_id = "gateway.example.com"
+/// rp_origin = "https://gateway.example.com"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct AdminAuthConfig {
+    /// How passkeys are used for admin sessions.
+    #[serde(default)]
+    pub passkeys: PasskeyPolicy,
+
+    /// Allow signing in to the admin UI with a passkey alone, as an
+    /// alternative to SSO. Such sessions carry no IdP groups or roles, and
+    /// organizations with `enforcement_mode = "required"` SSO still reject
+    /// them. When `false`, passkeys only verify existing SSO sessions.
+    #[serde(default = "default_true")]
+    pub passkey_login: bool,
+
+    /// WebAuthn relying party settings. Required unless `passkeys = "disabled"`.
+    #[serde(default)]
+    pub webauthn: Option<WebAuthnConfig>,
+}
+
+impl Default for AdminAuthConfig {
+    fn default() -> Self {
+        Self {
+            passkeys: PasskeyPolicy::default(),
+            passkey_login: true,
+            webauthn: None,
+        }
+    }
+}
+
+/// Passkey enforcement for admin sessions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum PasskeyPolicy {
+    /// WebAuthn endpoints are not mounted.
+    #[default]
+    Disabled,
+    /// Users may register passkeys and use them to sign in, but SSO sessions
+    /// work without one.
+    Optional,
+    /// Every SSO session must also be verified with a passkey before it can
+    /// use the admin API. Users without a passkey may still register their
+    /// first one from an unverified session.
+    Required,
+}
+
+/// WebAuthn relying party settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct WebAuthnConfig {
+    /// Relying party ID: the domain passkeys are bound to. Must be the host
+    /// of `rp_origin` or a parent domain of it. Changing it invalidates every
+    /// registered passkey.
+    pub rp_id: String,
+
+    /// Origin the admin UI is served from, e.g. `https://gateway.example.com`.
+    pub rp_origin: String,
+
+    /// Name shown by the browser and authenticator during registration.
+    #[serde(default = "default_webauthn_rp_name")]
+    pub rp_name: String,
+
+    /// How long a started registration or authentication stays valid, in
+    /// seconds.
+    #[serde(default = "default_webauthn_challenge_ttl")]
+    pub challenge_ttl_secs: u64,
+}
+
+fn default_webauthn_rp_name() -> String {
+    "Hadrian".to_string()
+}
+
+fn default_webauthn_challenge_ttl() -> u64 {
+    300
+}
+
+impl AdminAuthConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.passkeys == PasskeyPolicy::Disabled {
+            return Ok(());
+        }
+        if !cfg!(feature = "webauthn") {
+            return Err(ConfigError::Validation(
+                "auth.admin.passkeys requires the 'webauthn' feature to be enabled".into(),
+            ));
+        }
+        let Some(webauthn) = &self.webauthn else {
+            return Err(ConfigError::Validation(
+                "auth.admin.webauthn must be configured when passkeys are enabled".into(),
+            ));
+        };
+        webauthn.validate()
+    }
+}
+
+impl WebAuthnConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let rp_id = self.rp_id.trim().to_ascii_lowercase();
+        if rp_id.is_empty() || rp_id.contains(['/', ':']) {
+            return Err(ConfigError::Validation(
+                "auth.admin.webauthn.rp_id must be a bare domain name".into(),
+            ));
+        }
+        let origin = url::Url::parse(&self.rp_origin).map_err(|e| {
+            ConfigError::Validation(format!("auth.admin.webauthn.rp_origin is not a URL: {e}"))
+        })?;
+        let host = origin.host_str().unwrap_or_default().to_ascii_lowercase();
+        if !host_matches(&host, &rp_id) {
+            return Err(ConfigError::Validation(format!(
+                "auth.admin.webauthn.rp_id '{}' must be '{host}' or a parent domain of it",
+                self.rp_id
+            )));
+        }
+        if origin.scheme() != "https" && host != "localhost" {
+            return Err(ConfigError::Validation(
+                "auth.admin.webauthn.rp_origin must use https (except for localhost)".into(),
+            ));
+        }
+        if self.rp_name.trim().is_empty() {
+            return Err(ConfigError::Validation(
+                "auth.admin.webauthn.rp_name must not be empty".into(),
+            ));
+        }
+        if self.challenge_ttl_secs == 0 {
+            return Err(ConfigError::Validation(
+                "auth.admin.webauthn.challenge_ttl_secs must be greater than 0".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Token Exchange Configuration
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Exchange of long-lived API keys for short-lived tokens.
+///
+/// `POST /api/v1/auth/token` trades an API key for a JWT signed with
+/// `signing_key` that browser or edge clients can use in its place. Tokens can
+/// only narrow what the key allows (models, scopes, one project), count
+/// against the key's budgets and rate limits, and stop working when the key
+/// is revoked.
+///
+/// ```toml
+/// [auth.token_exchange]
+/// enabled = true
+/// signing_key = "${TOKEN_EXCHANGE_SIGNING_KEY}"
+/// default_ttl_secs = 900
+/// ```
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct TokenExchangeConfig {
+    /// Enable the exchange endpoint and accept exchanged tokens.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// HMAC-SHA256 secret tokens are signed with. At least 32 bytes; keep it
+    /// out of the config file, e.g. `"${TOKEN_EXCHANGE_SIGNING_KEY}"`.
+    /// Rotating it invalidates every outstanding token.
+    #[serde(default)]
+    pub signing_key: Option<String>,
+
+    /// `iss` claim of minted tokens. Must not collide with an `[[auth.jwt]]`
+    /// issuer.
+    #[serde(default = "default_token_exchange_issuer")]
+    pub issuer: String,
+
+    /// Lifetime of a token when the request doesn't ask for one, in seconds.
+    #[serde(default = "default_token_exchange_ttl")]
+    pub default_ttl_secs: u64,
+
+    /// Longest lifetime a request may ask for, in seconds.
+    #[serde(default = "default_token_exchange_max_ttl")]
+    pub max_ttl_secs: u64,
+}
+
+impl Default for TokenExchangeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            signing_key: None,
+            issuer: default_token_exchange_issuer(),
+            default_ttl_secs: default_token_exchange_ttl(),
+            max_ttl_secs: default_token_exchange_max_ttl(),
+        }
+    }
+}
+
+impl std::fmt::Debug for TokenExchangeConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TokenExchangeConfig")
+            .field("enabled", &self.enabled)
+            .field("signing_key", &self.signing_key.as_ref().map(|_| "****"))
+            .field("issuer", &self.issuer)
+            .field("default_ttl_secs", &self.default_ttl_secs)
+            .field("max_ttl_secs", &self.max_ttl_secs)
+            .finish()
+    }
+}
+
+fn default_token_exchange_issuer() -> String {
+    "hadrian-token-exchange".to_string()
+}
+
+fn default_token_exchange_ttl() -> u64 {
+    900
+}
+
+fn default_token_exchange_max_ttl() -> u64 {
+    3600
+}
+
+/// Minimum token exchange signing key length in bytes.
+const MIN_SIGNING_KEY_LEN: usize = 32;
+
+impl TokenExchangeConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if !cfg!(feature = "jwt") {
+            return Err(ConfigError::Validation(
+                "auth.token_exchange requires the 'jwt' feature to be enabled".into(),
+            ));
+        }
+        match &self.signing_key {
+            Some(key) if key.len() >= MIN_SIGNING_KEY_LEN => {}
+            _ => {
+                return Err(ConfigError::Validation(format!(
+                    "auth.token_exchange.signing_key must be set and at least \
+                     {MIN_SIGNING_KEY_LEN} bytes"
+                )));
+            }
+        }
+        if self.issuer.trim().is_empty() {
+            return Err(ConfigError::Validation(
+                "auth.token_exchange.issuer must not be empty".into(),
+            ));
+        }
+        if self.default_ttl_secs == 0 || self.default_ttl_secs > self.max_ttl_secs {
+            return Err(ConfigError::Validation(
+                "auth.token_exchange.default_ttl_secs must be between 1 and max_ttl_secs".into(),
+            ));
+        }
+        Ok(())
+    }
+}
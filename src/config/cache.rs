@@ -104,7 +104,10 @@ fn default_ttl() -> u64 {
 pub struct RedisCacheConfig {
     /// Redis connection URL.
     /// Format: redis://[user:password@]host:port[/database]
-    /// For clusters: redis+cluster://host1:port1,host2:port2,...
+    ///
+    /// In cluster and sentinel mode this may instead be a comma-separated
+    /// list of seed nodes or sentinels (`redis://host1:6379,host2:6379`),
+    /// unless `nodes` is set in the `cluster` / `sentinel` section.
     pub url: String,
 
     /// Connection timeout in seconds.
@@ -124,6 +127,15 @@ pub struct RedisCacheConfig {
     #[serde(default)]
     pub cluster: Option<RedisClusterConfig>,
 
+    /// Sentinel mode configuration. The master is discovered through the
+    /// sentinels and re-resolved after a failover.
+    #[serde(default)]
+    pub sentinel: Option<RedisSentinelConfig>,
+
+    /// In-process L1 cache in front of Redis. Disabled when absent.
+    #[serde(default)]
+    pub l1: Option<CacheL1Config>,
+
     /// TTL settings for specific cache types.
     #[serde(default)]
     pub ttl: CacheTtlConfig,
@@ -131,11 +143,52 @@ pub struct RedisCacheConfig {
 
 impl RedisCacheConfig {
     fn validate(&self) -> Result<(), ConfigError> {
-        if self.url.is_empty() {
+        let has_nodes = self.cluster.as_ref().is_some_and(|c| !c.nodes.is_empty())
+            || self.sentinel.as_ref().is_some_and(|s| !s.nodes.is_empty());
+        if self.url.is_empty() && !has_nodes {
             return Err(ConfigError::Validation("Redis URL cannot be empty".into()));
         }
+        if self.cluster.is_some() && self.sentinel.is_some() {
+            return Err(ConfigError::Validation(
+                "Redis cluster and sentinel modes are mutually exclusive".into(),
+            ));
+        }
+        if let Some(sentinel) = &self.sentinel
+            && sentinel.master_name.is_empty()
+        {
+            return Err(ConfigError::Validation(
+                "Redis sentinel master_name cannot be empty".into(),
+            ));
+        }
+        if let Some(l1) = &self.l1 {
+            l1.validate()?;
+        }
         Ok(())
     }
+
+    /// Seed node URLs for cluster or sentinel mode: the mode's `nodes` list if
+    /// set, otherwise the comma-separated `url`. Entries without a scheme get
+    /// `redis://` (or `rediss://` when `tls` is enabled).
+    pub fn node_urls(&self, nodes: &[String]) -> Vec<String> {
+        let scheme = if self.tls { "rediss://" } else { "redis://" };
+        let entries: Vec<&str> = if nodes.is_empty() {
+            self.url.split(',').collect()
+        } else {
+            nodes.iter().map(String::as_str).collect()
+        };
+        entries
+            .into_iter()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| {
+                if s.starts_with("redis://") || s.starts_with("rediss://") {
+                    s.to_string()
+                } else {
+                    format!("{scheme}{s}")
+                }
+            })
+            .collect()
+    }
 }
 
 /// Redis cluster configuration.
@@ -143,6 +196,12 @@ impl RedisCacheConfig {
 #[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(deny_unknown_fields)]
 pub struct RedisClusterConfig {
+    /// Seed nodes (`host:port` or `redis://host:port`). When empty, the
+    /// comma-separated `url` is used. Any reachable node is enough to
+    /// discover the rest of the cluster.
+    #[serde(default)]
+    pub nodes: Vec<String>,
+
     /// Read from replicas for read operations.
     #[serde(default)]
     pub read_from_replicas: bool,
@@ -164,6 +223,104 @@ pub struct RedisClusterConfig {
     pub response_timeout_secs: u64,
 }
 
+/// Redis Sentinel configuration.
+///
+/// ```toml
+/// [cache]
+/// type = "redis"
+/// url = "redis://sentinel-1:26379,sentinel-2:26379,sentinel-3:26379"
+///
+/// [cache.sentinel]
+/// master_name = "mymaster"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct RedisSentinelConfig {
+    /// Name of the monitored master (as in `SENTINEL MONITOR <name> ...`).
+    pub master_name: String,
+
+    /// Sentinel addresses (`host:port` or `redis://host:port`). When empty,
+    /// the comma-separated `url` is used.
+    #[serde(default)]
+    pub nodes: Vec<String>,
+
+    /// Username for the master, if it requires ACL authentication.
+    #[serde(default)]
+    pub master_username: Option<String>,
+
+    /// Password for the master. Sentinel credentials go in the sentinel URLs.
+    #[serde(default)]
+    pub master_password: Option<String>,
+
+    /// Database index on the master.
+    #[serde(default)]
+    pub db: i64,
+}
+
+/// In-process L1 cache layered in front of Redis.
+///
+/// Values read from or written to Redis are kept in a small local LRU for up
+/// to `ttl_secs`, so hot keys (API keys, sessions, policy versions) skip the
+/// network round trip. Writes go through to Redis and deletes evict the local
+/// copy, but other nodes keep serving their copy until it expires: `ttl_secs`
+/// bounds how stale a read can be after another node changes a key.
+///
+/// Counters, budgets and sets are never cached locally. Rate limits that are
+/// already exhausted are remembered until their window resets, so a client
+/// hammering past its limit is rejected without a Redis call.
+///
+/// ```toml
+/// [cache.l1]
+/// max_entries = 10000
+/// ttl_secs = 5
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct CacheL1Config {
+    /// Maximum number of entries held locally.
+    #[serde(default = "default_l1_max_entries")]
+    pub max_entries: usize,
+
+    /// How long a value is served locally before it is re-read from Redis.
+    #[serde(default = "default_l1_ttl")]
+    pub ttl_secs: u64,
+}
+
+impl Default for CacheL1Config {
+    fn default() -> Self {
+        Self {
+            max_entries: default_l1_max_entries(),
+            ttl_secs: default_l1_ttl(),
+        }
+    }
+}
+
+impl CacheL1Config {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.max_entries == 0 {
+            return Err(ConfigError::Validation(
+                "Cache l1.max_entries must be greater than 0".into(),
+            ));
+        }
+        if self.ttl_secs == 0 {
+            return Err(ConfigError::Validation(
+                "Cache l1.ttl_secs must be greater than 0".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn default_l1_max_entries() -> usize {
+    10_000
+}
+
+fn default_l1_ttl() -> u64 {
+    5
+}
+
 fn default_redis_timeout() -> u64 {
     5
 }
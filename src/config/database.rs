@@ -112,6 +112,16 @@ pub struct PostgresConfig {
     #[serde(default)]
     pub read_url: Option<String>,
 
+    /// Maximum replication lag in seconds before reads fall back to the
+    /// primary. 0 disables the lag check; reads still fall back when the
+    /// replica is unreachable.
+    #[serde(default = "default_replica_max_lag")]
+    pub replica_max_lag_secs: u64,
+
+    /// Interval between read replica health and lag checks in seconds.
+    #[serde(default = "default_replica_check_interval")]
+    pub replica_check_interval_secs: u64,
+
     /// Minimum number of connections in each pool.
     #[serde(default = "default_min_connections")]
     pub min_connections: u32,
@@ -154,6 +164,11 @@ impl PostgresConfig {
                 "min_connections cannot exceed max_connections".into(),
             ));
         }
+        if self.read_url.is_some() && self.replica_check_interval_secs == 0 {
+            return Err(ConfigError::Validation(
+                "replica_check_interval_secs must be greater than 0".into(),
+            ));
+        }
         Ok(())
     }
 }
@@ -201,3 +216,13 @@ fn default_connect_timeout() -> u64 {
 fn default_idle_timeout() -> u64 {
     300
 }
+
+#[cfg(feature = "database-postgres")]
+fn default_replica_max_lag() -> u64 {
+    30
+}
+
+#[cfg(feature = "database-postgres")]
+fn default_replica_check_interval() -> u64 {
+    10
+}
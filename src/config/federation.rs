@@ -0,0 +1,325 @@
+//! Multi-gateway federation.
+//!
+//! Gateways in different regions can each keep their own database and still
+//! be monitored from one place. A *satellite* periodically pushes daily usage
+//! totals and provider health to a *hub*, which stores them and exposes
+//! aggregated views through the Admin API. A gateway may be both, e.g. a
+//! regional hub that itself reports to a global one.
+//!
+//! # Example
+//!
+//! ```toml
+//! # On each satellite
+//! [federation.satellite]
+//! hub_url = "https://hub.example.com"
+//! gateway_id = "eu-west-1"
+//! region = "eu-west"
+//! token = "${FEDERATION_TOKEN}"
+//!
+//! # On the hub
+//! [[federation.hub.satellites]]
+//! id = "eu-west-1"
+//! token = "${EU_WEST_1_FEDERATION_TOKEN}"
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// Minimum length for federation tokens; they are long-lived shared secrets.
+const MIN_TOKEN_LEN: usize = 32;
+
+/// Federation configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct FederationConfig {
+    /// Push this gateway's usage and health to a hub.
+    #[serde(default)]
+    pub satellite: Option<FederationSatelliteConfig>,
+
+    /// Accept reports from satellite gateways.
+    #[serde(default)]
+    pub hub: Option<FederationHubConfig>,
+}
+
+impl FederationConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(satellite) = &self.satellite {
+            satellite.validate()?;
+        }
+        if let Some(hub) = &self.hub {
+            hub.validate()?;
+        }
+        Ok(())
+    }
+}
+
+/// Satellite settings: where and how often to report.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct FederationSatelliteConfig {
+    /// Base URL of the hub gateway. Reports are sent to
+    /// `{hub_url}/federation/v1/reports`.
+    pub hub_url: String,
+
+    /// ID this gateway reports as. Must match an entry in the hub's
+    /// `[[federation.hub.satellites]]`.
+    pub gateway_id: String,
+
+    /// Region label shown on the hub.
+    #[serde(default)]
+    pub region: Option<String>,
+
+    /// Shared secret sent as a bearer token. Prefer `${ENV_VAR}` expansion.
+    pub token: String,
+
+    /// How often to push a report (in seconds). Default: 300
+    #[serde(default = "default_report_interval_secs")]
+    pub interval_secs: u64,
+
+    /// Number of days of usage (including today) included in each report.
+    /// Resending recent days lets the hub catch up after missed reports.
+    /// Default: 2
+    #[serde(default = "default_lookback_days")]
+    pub lookback_days: u32,
+
+    /// Request timeout (in seconds). Default: 30
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_report_interval_secs() -> u64 {
+    300
+}
+
+fn default_lookback_days() -> u32 {
+    2
+}
+
+fn default_timeout_secs() -> u64 {
+    30
+}
+
+impl FederationSatelliteConfig {
+    /// Get the interval as a Duration.
+    pub fn interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.interval_secs)
+    }
+
+    /// Full URL of the hub's report endpoint.
+    pub fn reports_url(&self) -> String {
+        format!(
+            "{}/federation/v1/reports",
+            self.hub_url.trim_end_matches('/')
+        )
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        validate_gateway_id(&self.gateway_id, "[federation.satellite] gateway_id")?;
+        validate_token(&self.token, "[federation.satellite] token")?;
+        if self.interval_secs == 0 {
+            return Err("[federation.satellite] interval_secs must be > 0".into());
+        }
+        if self.lookback_days == 0 {
+            return Err("[federation.satellite] lookback_days must be > 0".into());
+        }
+        if self.timeout_secs == 0 {
+            return Err("[federation.satellite] timeout_secs must be > 0".into());
+        }
+        Ok(())
+    }
+}
+
+/// Hub settings: which satellites may report.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct FederationHubConfig {
+    /// Satellites allowed to report, each with its own token.
+    #[serde(default)]
+    pub satellites: Vec<FederatedSatelliteConfig>,
+}
+
+impl FederationHubConfig {
+    /// Find the satellite a bearer token belongs to.
+    ///
+    /// Compares against every configured token in constant time so the
+    /// response time doesn't reveal how much of a token matched.
+    pub fn satellite_for_token(&self, token: &str) -> Option<&FederatedSatelliteConfig> {
+        use subtle::ConstantTimeEq;
+
+        self.satellites.iter().fold(None, |found, satellite| {
+            let matches: bool = satellite.token.as_bytes().ct_eq(token.as_bytes()).into();
+            if matches { Some(satellite) } else { found }
+        })
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.satellites.is_empty() {
+            return Err("[federation.hub] requires at least one satellite".into());
+        }
+        let mut ids = std::collections::HashSet::new();
+        let mut tokens = std::collections::HashSet::new();
+        for satellite in &self.satellites {
+            validate_gateway_id(&satellite.id, "[[federation.hub.satellites]] id")?;
+            validate_token(
+                &satellite.token,
+                &format!("[[federation.hub.satellites]] '{}' token", satellite.id),
+            )?;
+            if !ids.insert(satellite.id.as_str()) {
+                return Err(format!(
+                    "[federation.hub] duplicate satellite id '{}'",
+                    satellite.id
+                ));
+            }
+            if !tokens.insert(satellite.token.as_str()) {
+                return Err(format!(
+                    "[federation.hub] satellite '{}' reuses another satellite's token",
+                    satellite.id
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A satellite the hub accepts reports from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct FederatedSatelliteConfig {
+    /// Satellite ID; reports must carry the same `gateway_id`.
+    pub id: String,
+
+    /// Shared secret the satellite authenticates with.
+    pub token: String,
+}
+
+fn validate_gateway_id(id: &str, field: &str) -> Result<(), String> {
+    let valid = !id.is_empty()
+        && id.len() <= 64
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "{field} must be 1-64 characters of letters, digits, '-', '_' or '.', got '{id}'"
+        ))
+    }
+}
+
+fn validate_token(token: &str, field: &str) -> Result<(), String> {
+    if token.len() < MIN_TOKEN_LEN {
+        return Err(format!(
+            "{field} must be at least {MIN_TOKEN_LEN} characters"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN_A: &str = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+    const TOKEN_B: &str = "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+
+    fn parse(toml_str: &str) -> FederationConfig {
+        toml::from_str(toml_str).unwrap()
+    }
+
+    #[test]
+    fn test_satellite_defaults() {
+        let config = parse(&format!(
+            r#"
+            [satellite]
+            hub_url = "https://hub.example.com/"
+            gateway_id = "eu-west-1"
+            token = "{TOKEN_A}"
+            "#
+        ));
+        let satellite = config.satellite.unwrap();
+        assert!(satellite.validate().is_ok());
+        assert_eq!(satellite.interval_secs, 300);
+        assert_eq!(satellite.lookback_days, 2);
+        assert_eq!(
+            satellite.reports_url(),
+            "https://hub.example.com/federation/v1/reports"
+        );
+    }
+
+    #[test]
+    fn test_satellite_validation() {
+        let config = parse(&format!(
+            r#"
+            [satellite]
+            hub_url = "https://hub.example.com"
+            gateway_id = "eu west"
+            token = "{TOKEN_A}"
+            "#
+        ));
+        assert!(config.validate().unwrap_err().contains("gateway_id"));
+
+        let config = parse(
+            r#"
+            [satellite]
+            hub_url = "https://hub.example.com"
+            gateway_id = "eu-west-1"
+            token = "short"
+            "#,
+        );
+        assert!(config.validate().unwrap_err().contains("token"));
+    }
+
+    #[test]
+    fn test_hub_validation() {
+        let config = parse(&format!(
+            r#"
+            [[hub.satellites]]
+            id = "eu-west-1"
+            token = "{TOKEN_A}"
+
+            [[hub.satellites]]
+            id = "eu-west-1"
+            token = "{TOKEN_B}"
+            "#
+        ));
+        assert!(config.validate().unwrap_err().contains("duplicate"));
+
+        let config = parse(&format!(
+            r#"
+            [[hub.satellites]]
+            id = "eu-west-1"
+            token = "{TOKEN_A}"
+
+            [[hub.satellites]]
+            id = "us-east-1"
+            token = "{TOKEN_A}"
+            "#
+        ));
+        assert!(config.validate().unwrap_err().contains("reuses"));
+
+        assert!(parse("[hub]").validate().is_err());
+    }
+
+    #[test]
+    fn test_satellite_for_token() {
+        let config = parse(&format!(
+            r#"
+            [[hub.satellites]]
+            id = "eu-west-1"
+            token = "{TOKEN_A}"
+
+            [[hub.satellites]]
+            id = "us-east-1"
+            token = "{TOKEN_B}"
+            "#
+        ));
+        let hub = config.hub.unwrap();
+        assert_eq!(hub.satellite_for_token(TOKEN_B).unwrap().id, "us-east-1");
+        assert_eq!(hub.satellite_for_token(TOKEN_A).unwrap().id, "eu-west-1");
+        assert!(hub.satellite_for_token("nope").is_none());
+    }
+}
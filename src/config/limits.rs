@@ -140,6 +140,10 @@ pub struct ResourceLimits {
     /// Maximum projects per team. Default: 100.
     #[serde(default = "default_max_projects_per_team")]
     pub max_projects_per_team: u32,
+
+    /// Maximum scheduled tasks per organization. Default: 20.
+    #[serde(default = "default_max_scheduled_tasks_per_org")]
+    pub max_scheduled_tasks_per_org: u32,
 }
 
 impl Default for ResourceLimits {
@@ -171,6 +175,7 @@ impl Default for ResourceLimits {
             max_members_per_project: default_max_members_per_project(),
             max_files_per_owner: default_max_files_per_owner(),
             max_projects_per_team: default_max_projects_per_team(),
+            max_scheduled_tasks_per_org: default_max_scheduled_tasks_per_org(),
         }
     }
 }
@@ -282,6 +287,10 @@ fn default_max_projects_per_team() -> u32 {
     100
 }
 
+fn default_max_scheduled_tasks_per_org() -> u32 {
+    20
+}
+
 /// Rate limiting defaults.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
@@ -328,6 +337,114 @@ pub struct RateLimitDefaults {
     /// When true, API keys can have any positive rate limit value.
     #[serde(default)]
     pub allow_per_key_above_global: bool,
+
+    /// Per-API-key burst smoothing. Queues short bursts instead of
+    /// rejecting them.
+    #[serde(default)]
+    pub smoothing: RateLimitSmoothingConfig,
+
+    /// Settings for `window_type = "token_bucket"`.
+    #[serde(default)]
+    pub token_bucket: TokenBucketConfig,
+}
+
+/// Token-bucket smoothing for bursty API keys.
+///
+/// Each API key gets a bucket of `burst` requests that refills at its
+/// requests-per-minute rate. A request that finds the bucket empty waits for
+/// the next token instead of failing, as long as that wait is at most
+/// `max_delay_ms`; otherwise it is rejected with 429. Buckets are kept per
+/// gateway node and run before the request-per-minute window check, which
+/// still applies.
+///
+/// ```toml
+/// [limits.rate_limits.smoothing]
+/// enabled = true
+/// burst = 10
+/// max_delay_ms = 2000
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct RateLimitSmoothingConfig {
+    /// Enable burst smoothing.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Requests a key can send back to back before being paced (minimum 1).
+    #[serde(default = "default_smoothing_burst")]
+    pub burst: u32,
+
+    /// Longest a request is held waiting for a token before it is rejected.
+    #[serde(default = "default_smoothing_max_delay_ms")]
+    pub max_delay_ms: u64,
+}
+
+impl Default for RateLimitSmoothingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            burst: default_smoothing_burst(),
+            max_delay_ms: default_smoothing_max_delay_ms(),
+        }
+    }
+}
+
+fn default_smoothing_burst() -> u32 {
+    10
+}
+
+fn default_smoothing_max_delay_ms() -> u64 {
+    2000
+}
+
+/// Token bucket request limits (`window_type = "token_bucket"`).
+///
+/// Each API key's request-per-minute limit becomes a bucket of that many
+/// tokens, refilled continuously rather than reset at window boundaries.
+/// With a Redis cache the bucket is updated atomically in Redis, so the limit
+/// holds across all gateway replicas. If Redis is unreachable each replica
+/// falls back to a local bucket of `1 / fallback_replicas` of the limit.
+///
+/// ```toml
+/// [limits.rate_limits]
+/// window_type = "token_bucket"
+///
+/// [limits.rate_limits.token_bucket]
+/// jitter_ratio = 0.2
+/// fallback_replicas = 4
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct TokenBucketConfig {
+    /// Random extra added to the `Retry-After` of rejected requests, as a
+    /// fraction of the wait for the next token (0.0-1.0), so limited clients
+    /// don't all retry at the same moment.
+    #[serde(default = "default_token_bucket_jitter_ratio")]
+    pub jitter_ratio: f64,
+
+    /// Number of replicas sharing the limit, used to size local buckets
+    /// while Redis is unavailable.
+    #[serde(default = "default_token_bucket_fallback_replicas")]
+    pub fallback_replicas: u32,
+}
+
+impl Default for TokenBucketConfig {
+    fn default() -> Self {
+        Self {
+            jitter_ratio: default_token_bucket_jitter_ratio(),
+            fallback_replicas: default_token_bucket_fallback_replicas(),
+        }
+    }
+}
+
+fn default_token_bucket_jitter_ratio() -> f64 {
+    0.1
+}
+
+fn default_token_bucket_fallback_replicas() -> u32 {
+    1
 }
 
 /// IP-based rate limiting configuration for unauthenticated traffic.
@@ -379,6 +496,8 @@ impl Default for RateLimitDefaults {
             estimated_tokens_per_request: default_estimated_tokens(),
             ip_rate_limits: IpRateLimitConfig::default(),
             allow_per_key_above_global: false,
+            smoothing: RateLimitSmoothingConfig::default(),
+            token_bucket: TokenBucketConfig::default(),
         }
     }
 }
@@ -409,6 +528,9 @@ pub enum RateLimitWindowType {
     /// Sliding window (rolling count over the interval).
     #[default]
     Sliding,
+    /// Token bucket refilled continuously at the per-minute rate. With a
+    /// Redis cache, every replica draws from the same bucket.
+    TokenBucket,
 }
 
 /// Budget defaults.
@@ -20,7 +20,9 @@ mod cache;
 mod database;
 mod docs;
 mod features;
+mod federation;
 mod limits;
+mod notifications;
 mod observability;
 mod providers;
 mod retention;
@@ -39,7 +41,9 @@ pub use cache::*;
 pub use database::*;
 pub use docs::*;
 pub use features::*;
+pub use federation::*;
 pub use limits::*;
+pub use notifications::*;
 pub use observability::*;
 pub use providers::*;
 pub use retention::*;
@@ -123,6 +127,14 @@ pub struct GatewayConfig {
     /// Sovereignty and compliance metadata configuration.
     #[serde(default)]
     pub sovereignty: SovereigntyConfig,
+
+    /// Outbound notification channels (email).
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+
+    /// Multi-gateway federation (satellite reporting and hub aggregation).
+    #[serde(default)]
+    pub federation: FederationConfig,
 }
 
 impl GatewayConfig {
@@ -187,6 +199,65 @@ impl GatewayConfig {
             ));
         }
 
+        // Client certificates authenticate as service accounts, which live in
+        // the database, and can only be requested when TLS is terminated here.
+        if let Some(tls) = &self.server.tls
+            && tls.client_auth.is_some()
+        {
+            if !cfg!(feature = "tls") {
+                return Err(ConfigError::Validation(
+                    "[server.tls.client_auth] requires native TLS, which this binary \
+                     was built without. Rebuild with the `tls` feature or verify \
+                     client certificates at your TLS-terminating proxy."
+                        .into(),
+                ));
+            }
+            if self.database.is_none() {
+                return Err(ConfigError::Validation(
+                    "[server.tls.client_auth] requires a database to map client \
+                     certificates to service accounts"
+                        .into(),
+                ));
+            }
+        }
+
+        if let Some(grpc) = &self.server.grpc {
+            if !cfg!(feature = "grpc") {
+                return Err(ConfigError::Validation(
+                    "[server.grpc] is set but this binary was built without the `grpc` \
+                     feature. Rebuild with the `grpc` feature or remove [server.grpc]."
+                        .into(),
+                ));
+            }
+            if grpc.port == self.server.port {
+                return Err(ConfigError::Validation(
+                    "[server.grpc] port must differ from server.port".into(),
+                ));
+            }
+        }
+
+        if let Some(graphql) = &self.server.graphql {
+            if !cfg!(feature = "graphql") {
+                return Err(ConfigError::Validation(
+                    "[server.graphql] is set but this binary was built without the `graphql` \
+                     feature. Rebuild with the `graphql` feature or remove [server.graphql]."
+                        .into(),
+                ));
+            }
+            if graphql.max_depth == 0 || graphql.max_complexity == 0 {
+                return Err(ConfigError::Validation(
+                    "[server.graphql] max_depth and max_complexity must be greater than 0".into(),
+                ));
+            }
+            if self.database.is_none() {
+                return Err(ConfigError::Validation(
+                    "[server.graphql] requires a database; the admin API is only served \
+                     with one"
+                        .into(),
+                ));
+            }
+        }
+
         // Validate individual sections
         self.database.validate()?;
         self.cache.validate()?;
@@ -194,6 +265,152 @@ impl GatewayConfig {
         self.providers.validate()?;
         self.storage.validate().map_err(ConfigError::Validation)?;
         self.features.validate().map_err(ConfigError::Validation)?;
+        self.observability
+            .validate()
+            .map_err(ConfigError::Validation)?;
+        self.notifications
+            .validate()
+            .map_err(ConfigError::Validation)?;
+
+        self.federation
+            .validate()
+            .map_err(ConfigError::Validation)?;
+        if self.federation.hub.is_some() && self.database.is_none() {
+            return Err(ConfigError::Validation(
+                "[federation.hub] requires a database to store satellite reports".into(),
+            ));
+        }
+        if let Some(satellite) = &self.federation.satellite {
+            if self.database.is_none() {
+                return Err(ConfigError::Validation(
+                    "[federation.satellite] requires a database to read usage from".into(),
+                ));
+            }
+            crate::validation::validate_base_url(
+                &satellite.hub_url,
+                self.server.allow_loopback_urls,
+            )
+            .map_err(|e| {
+                ConfigError::Validation(format!(
+                    "[federation.satellite] hub_url failed SSRF validation: {e}"
+                ))
+            })?;
+        }
+
+        let reports = &self.features.scheduled_reports;
+        if reports.enabled && reports.uses_email() && self.notifications.email.is_none() {
+            return Err(ConfigError::Validation(
+                "[features.scheduled_reports] email delivery requires [notifications.email]".into(),
+            ));
+        }
+        if reports.enabled && self.database.is_none() {
+            return Err(ConfigError::Validation(
+                "[features.scheduled_reports] requires a database to record report history".into(),
+            ));
+        }
+        for report in &reports.reports {
+            if let ReportDelivery::Webhook { url, .. } = &report.delivery {
+                crate::validation::validate_base_url(url, self.server.allow_loopback_urls)
+                    .map_err(|e| {
+                        ConfigError::Validation(format!(
+                            "[features.scheduled_reports] report '{}' webhook url failed SSRF validation: {}",
+                            report.name, e
+                        ))
+                    })?;
+            }
+        }
+
+        let invitations = &self.features.invitations;
+        if invitations.enabled {
+            if self.database.is_none() {
+                return Err(ConfigError::Validation(
+                    "[features.invitations] requires a database to store invitations".into(),
+                ));
+            }
+            if invitations.uses_email() && self.notifications.email.is_none() {
+                return Err(ConfigError::Validation(
+                    "[features.invitations] email delivery requires [notifications.email]".into(),
+                ));
+            }
+            if let Some(InvitationDelivery::Webhook { url, .. }) = &invitations.delivery {
+                crate::validation::validate_base_url(url, self.server.allow_loopback_urls)
+                    .map_err(|e| {
+                        ConfigError::Validation(format!(
+                            "[features.invitations] delivery webhook url failed SSRF validation: {e}"
+                        ))
+                    })?;
+            }
+        }
+
+        if self.features.elevations.enabled && self.database.is_none() {
+            return Err(ConfigError::Validation(
+                "[features.elevations] requires a database to store elevations".into(),
+            ));
+        }
+
+        if self.features.entitlements.enabled && self.database.is_none() {
+            return Err(ConfigError::Validation(
+                "[features.entitlements] requires a database to store organization plans".into(),
+            ));
+        }
+
+        let notifications = &self.notifications;
+        if (notifications.spend_alerts.enabled || notifications.access_review_reminders.enabled)
+            && self.database.is_none()
+        {
+            return Err(ConfigError::Validation(
+                "[notifications] spend alerts and access review reminders require a database to \
+                 look up organization members"
+                    .into(),
+            ));
+        }
+
+        let summaries = &self.features.conversation_summaries;
+        if summaries.enabled {
+            if self.database.is_none() {
+                return Err(ConfigError::Validation(
+                    "[features.conversation_summaries] requires a database to store summaries"
+                        .into(),
+                ));
+            }
+            if self.providers.get(&summaries.provider).is_none() {
+                return Err(ConfigError::Validation(format!(
+                    "[features.conversation_summaries] provider '{}' is not configured",
+                    summaries.provider
+                )));
+            }
+        }
+
+        if self.features.anomaly_detection.enabled && self.database.is_none() {
+            return Err(ConfigError::Validation(
+                "[features.anomaly_detection] requires a database for usage and findings".into(),
+            ));
+        }
+
+        let sync = &self.features.vector_store_sync;
+        if sync.enabled && !sync.sources.is_empty() {
+            if self.database.is_none() {
+                return Err(ConfigError::Validation(
+                    "[features.vector_store_sync] requires a database to track sync state".into(),
+                ));
+            }
+            if self.features.file_search.is_none() {
+                return Err(ConfigError::Validation(
+                    "[features.vector_store_sync] requires [features.file_search] to process synced documents".into(),
+                ));
+            }
+        }
+        for source in &sync.sources {
+            if let SyncConnector::Confluence { base_url, .. } = &source.connector {
+                crate::validation::validate_base_url(base_url, self.server.allow_loopback_urls)
+                    .map_err(|e| {
+                        ConfigError::Validation(format!(
+                            "[features.vector_store_sync] source '{}' base_url failed SSRF validation: {}",
+                            source.name, e
+                        ))
+                    })?;
+            }
+        }
 
         // SSRF-validate the responses webhook URL with the server's
         // loopback policy. Done here (not in features.validate) so the
@@ -0,0 +1,483 @@
+//! Outbound notification channels.
+//!
+//! Configures how the gateway sends messages to people (as opposed to
+//! machine-facing webhooks), and which notifications it sends. Currently
+//! only email over SMTP is supported.
+//!
+//! # Example
+//!
+//! ```toml
+//! [notifications.email]
+//! host = "smtp.example.com"
+//! port = 587
+//! tls = "starttls"
+//! username = "hadrian"
+//! password = "${SMTP_PASSWORD}"
+//! from = "Hadrian <noreply@example.com>"
+//! rate_limit_per_minute = 60
+//!
+//! [notifications.email.org_from]
+//! acme = "Acme AI <ai@acme.example.com>"
+//!
+//! [notifications.email.templates.spend_alert]
+//! subject = "[{{org_name}}] {{spend_percent}}% of the {{period}} budget used"
+//!
+//! [notifications.spend_alerts]
+//! enabled = true
+//!
+//! [notifications.dlq_failures]
+//! enabled = true
+//! to = ["oncall@example.com"]
+//! ```
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Notification channel configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct NotificationsConfig {
+    /// SMTP email delivery. Requires the `smtp` feature.
+    #[serde(default)]
+    pub email: Option<EmailConfig>,
+
+    /// Email organization owners and admins when a budget warning threshold
+    /// is reached.
+    #[serde(default)]
+    pub spend_alerts: SpendAlertNotifications,
+
+    /// Periodically email organization owners and admins a summary of stale
+    /// users and API keys to review.
+    #[serde(default)]
+    pub access_review_reminders: AccessReviewReminderNotifications,
+
+    /// Email operators when a dead-letter queue entry exhausts its retries.
+    #[serde(default)]
+    pub dlq_failures: DlqFailureNotifications,
+}
+
+impl NotificationsConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(ref email) = self.email {
+            email.validate()?;
+        }
+        for (section, enabled) in [
+            ("spend_alerts", self.spend_alerts.enabled),
+            (
+                "access_review_reminders",
+                self.access_review_reminders.enabled,
+            ),
+            ("dlq_failures", self.dlq_failures.enabled),
+        ] {
+            if enabled && self.email.is_none() {
+                return Err(format!(
+                    "[notifications.{section}] requires [notifications.email]"
+                ));
+            }
+            if enabled && !cfg!(feature = "smtp") {
+                return Err(format!(
+                    "[notifications.{section}] requires the 'smtp' feature"
+                ));
+            }
+        }
+        if self.spend_alerts.enabled
+            && self.spend_alerts.roles.is_empty()
+            && self.spend_alerts.to.is_empty()
+        {
+            return Err("[notifications.spend_alerts] needs roles or to addresses".into());
+        }
+        self.access_review_reminders.validate()?;
+        if self.dlq_failures.enabled && self.dlq_failures.to.is_empty() {
+            return Err("[notifications.dlq_failures] to must not be empty".into());
+        }
+        for address in self.spend_alerts.to.iter().chain(&self.dlq_failures.to) {
+            if !address.contains('@') {
+                return Err(format!(
+                    "[notifications] '{address}' is not an email address"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// SMTP server settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct EmailConfig {
+    /// SMTP server hostname.
+    pub host: String,
+
+    /// SMTP server port. Default: 587
+    #[serde(default = "default_smtp_port")]
+    pub port: u16,
+
+    /// Transport security. Default: `starttls`
+    #[serde(default)]
+    pub tls: SmtpTls,
+
+    /// SMTP username. Authentication is skipped when unset.
+    #[serde(default)]
+    pub username: Option<String>,
+
+    /// SMTP password. Prefer `${ENV_VAR}` expansion over a literal value.
+    #[serde(default)]
+    pub password: Option<String>,
+
+    /// Sender address, e.g. `"Hadrian <noreply@example.com>"`.
+    pub from: String,
+
+    /// Timeout for a single send, in seconds. Default: 30
+    #[serde(default = "default_smtp_timeout_secs")]
+    pub timeout_secs: u64,
+
+    /// Maximum emails sent per minute by each gateway instance. Emails over
+    /// the limit are dropped and recorded as `rate_limited` in the send log.
+    /// 0 disables the limit. Default: 60
+    #[serde(default = "default_smtp_rate_limit_per_minute")]
+    pub rate_limit_per_minute: u32,
+
+    /// Sender addresses for individual organizations, keyed by organization
+    /// slug. Organizations not listed use `from`.
+    #[serde(default)]
+    pub org_from: HashMap<String, String>,
+
+    /// Subject and body overrides for each kind of notification email.
+    #[serde(default)]
+    pub templates: EmailTemplates,
+}
+
+impl EmailConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.host.trim().is_empty() {
+            return Err("[notifications.email] host must not be empty".into());
+        }
+        if !self.from.contains('@') {
+            return Err(format!(
+                "[notifications.email] from must be an email address, got '{}'",
+                self.from
+            ));
+        }
+        if self.username.is_some() != self.password.is_some() {
+            return Err("[notifications.email] username and password must be set together".into());
+        }
+        for (slug, from) in &self.org_from {
+            if !from.contains('@') {
+                return Err(format!(
+                    "[notifications.email.org_from] {slug} must be an email address, got '{from}'"
+                ));
+            }
+        }
+        self.templates.validate()
+    }
+
+    /// Sender address for an organization.
+    pub fn from_for_org(&self, org_slug: Option<&str>) -> &str {
+        org_slug
+            .and_then(|slug| self.org_from.get(slug))
+            .unwrap_or(&self.from)
+    }
+}
+
+fn default_smtp_port() -> u16 {
+    587
+}
+
+fn default_smtp_timeout_secs() -> u64 {
+    30
+}
+
+fn default_smtp_rate_limit_per_minute() -> u32 {
+    60
+}
+
+/// Template overrides per notification kind. Unset templates, and unset
+/// fields of a template, use the built-in text.
+///
+/// Templates substitute `{{name}}` placeholders with the variables of their
+/// kind; unknown placeholders are left as-is.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct EmailTemplates {
+    /// Invitation emails. Variables: `org_name`, `team_name`, `target`,
+    /// `role`, `invite_url`, `expires_at`.
+    #[serde(default)]
+    pub invitation: Option<EmailTemplate>,
+
+    /// Spend alerts. Variables: `org_name`, `scope`, `spend_percent`,
+    /// `current_spend`, `limit`, `period`.
+    #[serde(default)]
+    pub spend_alert: Option<EmailTemplate>,
+
+    /// Access review reminders. Variables: `org_name`, `inactive_days`,
+    /// `stale_users`, `never_active_users`, `stale_api_keys`, `details`.
+    #[serde(default)]
+    pub access_review_reminder: Option<EmailTemplate>,
+
+    /// Dead-letter queue failures. Variables: `entry_id`, `entry_type`,
+    /// `retry_count`, `error`, `created_at`.
+    #[serde(default)]
+    pub dlq_failure: Option<EmailTemplate>,
+}
+
+impl EmailTemplates {
+    fn validate(&self) -> Result<(), String> {
+        for (kind, template) in [
+            ("invitation", &self.invitation),
+            ("spend_alert", &self.spend_alert),
+            ("access_review_reminder", &self.access_review_reminder),
+            ("dlq_failure", &self.dlq_failure),
+        ] {
+            let Some(template) = template else { continue };
+            if template
+                .subject
+                .as_deref()
+                .is_some_and(|s| s.trim().is_empty())
+                || template
+                    .text
+                    .as_deref()
+                    .is_some_and(|t| t.trim().is_empty())
+            {
+                return Err(format!(
+                    "[notifications.email.templates.{kind}] subject and text must not be empty"
+                ));
+            }
+            if template
+                .subject
+                .as_deref()
+                .is_some_and(|s| s.contains('\n'))
+            {
+                return Err(format!(
+                    "[notifications.email.templates.{kind}] subject must be a single line"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Subject and plain-text body of a notification email.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct EmailTemplate {
+    /// Subject line.
+    #[serde(default)]
+    pub subject: Option<String>,
+
+    /// Plain-text body.
+    #[serde(default)]
+    pub text: Option<String>,
+}
+
+/// Spend alert emails, sent once per API key and budget period when its
+/// spend crosses `[limits.budgets] warning_threshold`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct SpendAlertNotifications {
+    /// Send spend alerts. Default: false
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Organization roles whose members are emailed. Default: owner, admin
+    #[serde(default = "default_notification_roles")]
+    pub roles: Vec<String>,
+
+    /// Additional addresses emailed for every organization's alerts.
+    #[serde(default)]
+    pub to: Vec<String>,
+}
+
+impl Default for SpendAlertNotifications {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            roles: default_notification_roles(),
+            to: Vec::new(),
+        }
+    }
+}
+
+/// Access review reminder emails. Each organization with stale access gets
+/// a summary of users and API keys inactive for `inactive_days`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct AccessReviewReminderNotifications {
+    /// Send reminders. Default: false
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Days between reminders. Default: 30
+    #[serde(default = "default_access_review_interval_days")]
+    pub interval_days: u32,
+
+    /// Days without activity after which a user or API key is stale.
+    /// Default: 90
+    #[serde(default = "default_access_review_inactive_days")]
+    pub inactive_days: u32,
+
+    /// Organization roles whose members are emailed. Default: owner, admin
+    #[serde(default = "default_notification_roles")]
+    pub roles: Vec<String>,
+}
+
+impl Default for AccessReviewReminderNotifications {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_days: default_access_review_interval_days(),
+            inactive_days: default_access_review_inactive_days(),
+            roles: default_notification_roles(),
+        }
+    }
+}
+
+impl AccessReviewReminderNotifications {
+    fn validate(&self) -> Result<(), String> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.interval_days == 0 || self.inactive_days == 0 {
+            return Err(
+                "[notifications.access_review_reminders] interval_days and inactive_days must be \
+                 greater than 0"
+                    .into(),
+            );
+        }
+        if self.roles.is_empty() {
+            return Err("[notifications.access_review_reminders] roles must not be empty".into());
+        }
+        Ok(())
+    }
+}
+
+fn default_access_review_interval_days() -> u32 {
+    30
+}
+
+fn default_access_review_inactive_days() -> u32 {
+    90
+}
+
+fn default_notification_roles() -> Vec<String> {
+    vec!["owner".to_string(), "admin".to_string()]
+}
+
+/// Dead-letter queue failure emails, sent when the DLQ retry worker gives
+/// up on an entry after `[observability.dead_letter_queue.retry]
+/// max_retries` attempts.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct DlqFailureNotifications {
+    /// Send failure emails. Default: false
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Addresses emailed.
+    #[serde(default)]
+    pub to: Vec<String>,
+}
+
+/// SMTP transport security.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum SmtpTls {
+    /// Upgrade a plaintext connection with STARTTLS (usually port 587).
+    #[default]
+    Starttls,
+    /// Implicit TLS from the first byte (usually port 465).
+    Tls,
+    /// No encryption. Only for local relays and testing.
+    None,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_email_config_defaults() {
+        let config: NotificationsConfig = toml::from_str(
+            r#"
+            [email]
+            host = "smtp.example.com"
+            from = "noreply@example.com"
+            "#,
+        )
+        .unwrap();
+        let email = config.email.unwrap();
+        assert_eq!(email.port, 587);
+        assert_eq!(email.tls, SmtpTls::Starttls);
+        assert_eq!(email.timeout_secs, 30);
+        assert_eq!(email.rate_limit_per_minute, 60);
+        assert!(email.validate().is_ok());
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_org_from_override() {
+        let config: EmailConfig = toml::from_str(
+            r#"
+            host = "smtp.example.com"
+            from = "noreply@example.com"
+
+            [org_from]
+            acme = "Acme <ai@acme.example.com>"
+            "#,
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.from_for_org(Some("acme")),
+            "Acme <ai@acme.example.com>"
+        );
+        assert_eq!(config.from_for_org(Some("other")), "noreply@example.com");
+        assert_eq!(config.from_for_org(None), "noreply@example.com");
+    }
+
+    #[test]
+    fn test_notifications_require_email() {
+        let config: NotificationsConfig = toml::from_str(
+            r#"
+            [spend_alerts]
+            enabled = true
+            "#,
+        )
+        .unwrap();
+        assert!(config.validate().is_err());
+
+        let config: NotificationsConfig = toml::from_str(
+            r#"
+            [email]
+            host = "smtp.example.com"
+            from = "noreply@example.com"
+
+            [dlq_failures]
+            enabled = true
+            "#,
+        )
+        .unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_email_config_requires_credentials_pair() {
+        let config: EmailConfig = toml::from_str(
+            r#"
+            host = "smtp.example.com"
+            from = "noreply@example.com"
+            username = "hadrian"
+            "#,
+        )
+        .unwrap();
+        assert!(config.validate().is_err());
+    }
+}
@@ -31,6 +31,30 @@ pub struct ObservabilityConfig {
     /// Validates API responses against the OpenAI OpenAPI specification.
     #[serde(default)]
     pub response_validation: ResponseValidationConfig,
+
+    /// Latency and availability service level objectives for LLM requests.
+    #[serde(default)]
+    pub slos: Vec<SloConfig>,
+}
+
+impl ObservabilityConfig {
+    /// Validate the observability configuration.
+    pub fn validate(&self) -> Result<(), String> {
+        let mut names = std::collections::HashSet::new();
+        for slo in &self.slos {
+            slo.validate()?;
+            if !names.insert(slo.name.as_str()) {
+                return Err(format!(
+                    "[[observability.slos]] name '{}' is used more than once",
+                    slo.name
+                ));
+            }
+        }
+        if let Some(dlq) = &self.dead_letter_queue {
+            dlq.validate()?;
+        }
+        Ok(())
+    }
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -596,6 +620,123 @@ fn default_true() -> bool {
     true
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// SLOs
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Longest supported SLO compliance window (30 days).
+pub const MAX_SLO_WINDOW_SECS: u64 = 30 * 24 * 3600;
+
+/// A service level objective over LLM requests.
+///
+/// Each request matching `provider` and `model` is counted as good or bad for
+/// the SLO's indicator. Compliance is the fraction of good requests over the
+/// rolling `window_secs`, and burn rates compare the recent bad fraction to
+/// the error budget (`1 - objective`).
+///
+/// ```toml
+/// # p95 time to first token under 800ms for gpt-4o
+/// [[observability.slos]]
+/// name = "gpt-4o-ttft"
+/// model = "gpt-4o*"
+/// indicator = "ttft"
+/// threshold_ms = 800
+/// objective = 0.95
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct SloConfig {
+    /// Unique name, used as the `slo` label on SLO metrics.
+    pub name: String,
+
+    /// Provider the SLO applies to. Applies to every provider when unset.
+    #[serde(default)]
+    pub provider: Option<String>,
+
+    /// Model the SLO applies to. A trailing `*` matches any suffix
+    /// (`gpt-4o*`). Applies to every model when unset.
+    #[serde(default)]
+    pub model: Option<String>,
+
+    /// What is measured for each request.
+    pub indicator: SloIndicator,
+
+    /// Latency threshold for `ttft` and `latency` SLOs. Requests at or under
+    /// it are good. Not used for `availability`.
+    #[serde(default)]
+    pub threshold_ms: Option<u64>,
+
+    /// Target fraction of good requests, between 0 and 1 exclusive.
+    /// `0.95` with a latency threshold reads as "p95 under the threshold".
+    pub objective: f64,
+
+    /// Rolling compliance window in seconds. Defaults to 30 days.
+    #[serde(default = "default_slo_window_secs")]
+    pub window_secs: u64,
+}
+
+impl SloConfig {
+    /// Whether a request to `provider`/`model` counts towards this SLO.
+    pub fn matches(&self, provider: &str, model: &str) -> bool {
+        self.provider.as_deref().is_none_or(|p| p == provider)
+            && self
+                .model
+                .as_deref()
+                .is_none_or(|m| crate::models::model_matches_pattern(model, m))
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let name = &self.name;
+        if name.is_empty() {
+            return Err("[[observability.slos]] name must not be empty".into());
+        }
+        if !(self.objective > 0.0 && self.objective < 1.0) {
+            return Err(format!(
+                "[[observability.slos]] '{name}' objective must be between 0 and 1 exclusive"
+            ));
+        }
+        match (self.indicator, self.threshold_ms) {
+            (SloIndicator::Availability, Some(_)) => {
+                return Err(format!(
+                    "[[observability.slos]] '{name}' threshold_ms does not apply to availability"
+                ));
+            }
+            (SloIndicator::Ttft | SloIndicator::Latency, None | Some(0)) => {
+                return Err(format!(
+                    "[[observability.slos]] '{name}' requires a threshold_ms greater than 0"
+                ));
+            }
+            _ => {}
+        }
+        if !(3600..=MAX_SLO_WINDOW_SECS).contains(&self.window_secs) {
+            return Err(format!(
+                "[[observability.slos]] '{name}' window_secs must be between 3600 and {MAX_SLO_WINDOW_SECS}"
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn default_slo_window_secs() -> u64 {
+    MAX_SLO_WINDOW_SECS
+}
+
+/// What an SLO measures for each request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum SloIndicator {
+    /// Time to first token of streamed responses.
+    Ttft,
+    /// Total request duration, to the end of the stream for streamed
+    /// responses. Only successful requests count.
+    Latency,
+    /// Requests that don't fail with a server error or a broken stream.
+    Availability,
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Dead Letter Queue
 // ─────────────────────────────────────────────────────────────────────────────
@@ -619,6 +760,9 @@ pub enum DeadLetterQueueConfig {
         /// Retry configuration.
         #[serde(default)]
         retry: DlqRetryConfig,
+        /// Scheduled redrive policies.
+        #[serde(default)]
+        redrive_policies: Vec<DlqRedrivePolicy>,
     },
 
     /// Redis-based dead-letter queue.
@@ -637,6 +781,9 @@ pub enum DeadLetterQueueConfig {
         /// Retry configuration.
         #[serde(default)]
         retry: DlqRetryConfig,
+        /// Scheduled redrive policies.
+        #[serde(default)]
+        redrive_policies: Vec<DlqRedrivePolicy>,
     },
 
     /// Database-based dead-letter queue.
@@ -653,6 +800,9 @@ pub enum DeadLetterQueueConfig {
         /// Retry configuration.
         #[serde(default)]
         retry: DlqRetryConfig,
+        /// Scheduled redrive policies.
+        #[serde(default)]
+        redrive_policies: Vec<DlqRedrivePolicy>,
     },
 }
 
@@ -674,6 +824,35 @@ impl DeadLetterQueueConfig {
             DeadLetterQueueConfig::Database { ttl_secs, .. } => *ttl_secs,
         }
     }
+
+    /// Get the scheduled redrive policies for any DLQ type.
+    pub fn redrive_policies(&self) -> &[DlqRedrivePolicy] {
+        match self {
+            DeadLetterQueueConfig::File {
+                redrive_policies, ..
+            } => redrive_policies,
+            DeadLetterQueueConfig::Redis {
+                redrive_policies, ..
+            } => redrive_policies,
+            DeadLetterQueueConfig::Database {
+                redrive_policies, ..
+            } => redrive_policies,
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let mut names = std::collections::HashSet::new();
+        for policy in self.redrive_policies() {
+            policy.validate()?;
+            if !names.insert(policy.name.as_str()) {
+                return Err(format!(
+                    "[[observability.dead_letter_queue.redrive_policies]] name '{}' is used more than once",
+                    policy.name
+                ));
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Configuration for DLQ retry processing.
@@ -722,6 +901,136 @@ impl Default for DlqRetryConfig {
     }
 }
 
+/// A scheduled redrive of DLQ entries matching a filter, run by the
+/// `dlq_redrive` job independently of the retry worker's backoff. Use it to
+/// replay entries once a known cause is fixed, e.g. a provider outage.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct DlqRedrivePolicy {
+    /// Policy name, used in logs and metrics.
+    pub name: String,
+    /// Interval between runs of this policy in seconds.
+    #[serde(default = "default_dlq_redrive_interval")]
+    pub interval_secs: u64,
+    /// Only redrive entries of this type (e.g. "usage_log").
+    #[serde(default)]
+    pub entry_type: Option<String>,
+    /// Only redrive entries whose error falls in this class.
+    #[serde(default)]
+    pub error_class: Option<DlqErrorClass>,
+    /// Only redrive entries whose error contains this text (case-insensitive).
+    #[serde(default)]
+    pub error_contains: Option<String>,
+    /// Only redrive entries for this provider.
+    #[serde(default)]
+    pub provider: Option<String>,
+    /// Only redrive entries at least this old, in seconds.
+    #[serde(default)]
+    pub min_age_secs: u64,
+    /// Only redrive entries at most this old, in seconds.
+    #[serde(default)]
+    pub max_age_secs: Option<u64>,
+    /// Only redrive entries retried fewer than this many times.
+    #[serde(default = "default_dlq_max_retries")]
+    pub max_retries: i32,
+    /// Maximum entries to redrive per run.
+    #[serde(default = "default_dlq_batch_size")]
+    pub batch_size: i64,
+    /// JSON merge patch (RFC 7386) applied to each payload before it's
+    /// replayed. The stored entry is left as is.
+    #[serde(default)]
+    pub patch: Option<serde_json::Value>,
+}
+
+impl DlqRedrivePolicy {
+    fn validate(&self) -> Result<(), String> {
+        let name = &self.name;
+        if name.trim().is_empty() {
+            return Err(
+                "[[observability.dead_letter_queue.redrive_policies]] name must not be empty"
+                    .to_string(),
+            );
+        }
+        if self.interval_secs == 0 {
+            return Err(format!(
+                "[[observability.dead_letter_queue.redrive_policies]] '{name}' interval_secs must be greater than 0"
+            ));
+        }
+        if self.batch_size <= 0 {
+            return Err(format!(
+                "[[observability.dead_letter_queue.redrive_policies]] '{name}' batch_size must be greater than 0"
+            ));
+        }
+        if let Some(max_age_secs) = self.max_age_secs
+            && max_age_secs <= self.min_age_secs
+        {
+            return Err(format!(
+                "[[observability.dead_letter_queue.redrive_policies]] '{name}' max_age_secs must be greater than min_age_secs"
+            ));
+        }
+        if let Some(patch) = &self.patch
+            && !patch.is_object()
+        {
+            return Err(format!(
+                "[[observability.dead_letter_queue.redrive_policies]] '{name}' patch must be a table"
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Broad cause of a DLQ entry's failure, derived from its error message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum DlqErrorClass {
+    /// The operation timed out.
+    Timeout,
+    /// The target was unreachable: refused or dropped connections, exhausted
+    /// pools, unavailable services.
+    Connection,
+    /// The target rejected the write, e.g. a unique or foreign key violation.
+    Constraint,
+    /// The payload couldn't be decoded or was rejected as invalid.
+    Payload,
+    /// Anything else.
+    Other,
+}
+
+impl DlqErrorClass {
+    /// Classify an error message.
+    pub fn of(error: &str) -> Self {
+        let error = error.to_lowercase();
+        let has = |needles: &[&str]| needles.iter().any(|n| error.contains(n));
+        if has(&["timed out", "timeout", "deadline"]) {
+            Self::Timeout
+        } else if has(&[
+            "connect",
+            "refused",
+            "reset by peer",
+            "broken pipe",
+            "unavailable",
+            "pool",
+        ]) {
+            Self::Connection
+        } else if has(&["constraint", "unique", "foreign key", "duplicate"]) {
+            Self::Constraint
+        } else if has(&[
+            "deserializ",
+            "invalid",
+            "missing field",
+            "expected",
+            "parse",
+        ]) {
+            Self::Payload
+        } else {
+            Self::Other
+        }
+    }
+}
+
 fn default_dlq_max_file_size() -> u64 {
     100 // 100 MB
 }
@@ -746,6 +1055,10 @@ fn default_dlq_table() -> String {
     "dead_letter_queue".to_string()
 }
 
+fn default_dlq_redrive_interval() -> u64 {
+    300 // 5 minutes
+}
+
 fn default_dlq_retry_interval() -> u64 {
     60 // 1 minute
 }
@@ -879,6 +1192,14 @@ pub struct UsageBufferConfig {
     /// Default: 10x max_size (10,000 entries at ~1KB each = ~10MB max memory).
     #[serde(default = "default_max_pending_entries")]
     pub max_pending_entries: usize,
+
+    /// Write-ahead log for buffered entries.
+    ///
+    /// Entries are persisted before they're flushed, so those still in the
+    /// buffer when the process crashes are recovered and flushed on the next
+    /// start. Without it, a crash loses up to `max_pending_entries` records.
+    #[serde(default)]
+    pub wal: Option<UsageWalConfig>,
 }
 
 impl Default for UsageBufferConfig {
@@ -887,6 +1208,7 @@ impl Default for UsageBufferConfig {
             max_size: default_usage_buffer_size(),
             flush_interval_ms: default_usage_flush_interval_ms(),
             max_pending_entries: default_max_pending_entries(),
+            wal: None,
         }
     }
 }
@@ -903,6 +1225,67 @@ fn default_max_pending_entries() -> usize {
     10_000 // 10x default max_size
 }
 
+/// Write-ahead log for the usage buffer.
+///
+/// # Example
+///
+/// ```toml
+/// [observability.usage.buffer.wal]
+/// type = "file"
+/// path = "data/usage.wal"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[serde(deny_unknown_fields)]
+pub enum UsageWalConfig {
+    /// Append-only file on local disk.
+    ///
+    /// Survives process crashes and container restarts, but not the loss of
+    /// the disk, so use a volume that outlives the container.
+    File {
+        /// Path to the log file.
+        path: String,
+        /// Sync each write to disk before the entries are flushed. Without
+        /// it, entries survive a process crash but not a machine crash.
+        #[serde(default = "default_true")]
+        fsync: bool,
+        /// Size in MB at which the file is rewritten without the entries
+        /// that have been flushed.
+        #[serde(default = "default_usage_wal_max_file_size")]
+        max_file_size_mb: u64,
+    },
+
+    /// Redis stream shared by all replicas.
+    ///
+    /// Each replica recovers its own entries when it restarts, and takes
+    /// over the entries of replicas that stopped without flushing them once
+    /// they are older than `orphan_after_secs`.
+    Redis {
+        /// Redis URL (can reuse cache URL).
+        url: String,
+        /// Stream key.
+        #[serde(default = "default_usage_wal_key")]
+        key: String,
+        /// Age after which another replica's unflushed entries are taken
+        /// over. Keep it well above the time a flush normally takes.
+        #[serde(default = "default_usage_wal_orphan_after")]
+        orphan_after_secs: u64,
+    },
+}
+
+fn default_usage_wal_max_file_size() -> u64 {
+    64
+}
+
+fn default_usage_wal_key() -> String {
+    "gw:usage_wal".to_string()
+}
+
+fn default_usage_wal_orphan_after() -> u64 {
+    300
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Response Validation
 // ─────────────────────────────────────────────────────────────────────────────
@@ -1106,4 +1489,111 @@ mod tests {
         // Other SIEM fields should have defaults
         assert_eq!(config.siem.device_product, "Gateway");
     }
+
+    #[test]
+    fn test_slo_validation() {
+        let parse = |toml: &str| toml::from_str::<ObservabilityConfig>(toml).unwrap();
+
+        let config = parse(
+            r#"
+            [[slos]]
+            name = "gpt-4o-ttft"
+            model = "gpt-4o*"
+            indicator = "ttft"
+            threshold_ms = 800
+            objective = 0.95
+        "#,
+        );
+        assert!(config.validate().is_ok());
+        assert_eq!(config.slos[0].window_secs, MAX_SLO_WINDOW_SECS);
+        assert!(config.slos[0].matches("openai", "gpt-4o-mini"));
+        assert!(!config.slos[0].matches("openai", "gpt-4.1"));
+
+        let missing_threshold = parse(
+            r#"
+            [[slos]]
+            name = "latency"
+            indicator = "latency"
+            objective = 0.99
+        "#,
+        );
+        assert!(missing_threshold.validate().is_err());
+
+        let bad_objective = parse(
+            r#"
+            [[slos]]
+            name = "availability"
+            indicator = "availability"
+            objective = 1.0
+        "#,
+        );
+        assert!(bad_objective.validate().is_err());
+
+        let duplicate = parse(
+            r#"
+            [[slos]]
+            name = "availability"
+            indicator = "availability"
+            objective = 0.99
+
+            [[slos]]
+            name = "availability"
+            provider = "openai"
+            indicator = "availability"
+            objective = 0.999
+        "#,
+        );
+        assert!(duplicate.validate().unwrap_err().contains("more than once"));
+    }
+
+    #[test]
+    fn test_dlq_redrive_policy_validation() {
+        let parse = |toml: &str| toml::from_str::<ObservabilityConfig>(toml).unwrap();
+
+        let config = parse(
+            r#"
+            [dead_letter_queue]
+            type = "database"
+
+            [[dead_letter_queue.redrive_policies]]
+            name = "db-outage"
+            error_class = "connection"
+            min_age_secs = 600
+            patch = { model = "gpt-4o" }
+        "#,
+        );
+        assert!(config.validate().is_ok());
+        let policy = &config.dead_letter_queue.unwrap().redrive_policies()[0];
+        assert_eq!(policy.interval_secs, 300);
+        assert_eq!(policy.error_class, Some(DlqErrorClass::Connection));
+        assert_eq!(policy.max_retries, 10);
+
+        let bad_ages = parse(
+            r#"
+            [dead_letter_queue]
+            type = "database"
+
+            [[dead_letter_queue.redrive_policies]]
+            name = "recent"
+            min_age_secs = 600
+            max_age_secs = 60
+        "#,
+        );
+        assert!(bad_ages.validate().is_err());
+
+        let duplicate = parse(
+            r#"
+            [dead_letter_queue]
+            type = "database"
+
+            [[dead_letter_queue.redrive_policies]]
+            name = "db-outage"
+
+            [[dead_letter_queue.redrive_policies]]
+            name = "db-outage"
+            provider = "openai"
+        "#,
+        );
+        assert!(duplicate.validate().unwrap_err().contains("more than once"));
+    }
 }
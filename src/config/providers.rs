@@ -102,6 +102,18 @@ pub struct ModelConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub open_weights: Option<bool>,
 
+    /// Knowledge cutoff date (YYYY-MM or YYYY-MM-DD).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub knowledge_cutoff: Option<String>,
+
+    /// Date the provider deprecated the model (YYYY-MM-DD).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecation_date: Option<String>,
+
+    /// Date the provider stops serving the model (YYYY-MM-DD).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sunset_date: Option<String>,
+
     /// Supported image sizes for image generation models.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub image_sizes: Vec<String>,
@@ -121,6 +133,34 @@ pub struct ModelConfig {
     /// Sovereignty and compliance metadata override for this model.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sovereignty: Option<SovereigntyMetadata>,
+
+    /// Hedge slow requests to this model with its first fallback.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hedge: Option<HedgeConfig>,
+}
+
+/// Request hedging for a latency-sensitive model.
+///
+/// If the provider hasn't responded within `delay_ms`, the same request is
+/// also sent to the first fallback in the model's fallback chain (skipping
+/// fallbacks with an open circuit breaker or that fail the request's
+/// sovereignty requirements). Whichever usable response arrives first is
+/// returned and the other request is cancelled. For streaming requests the
+/// race is decided by which stream starts first.
+///
+/// A good delay is around the model's p95 latency, so only the slowest
+/// requests pay for a second call.
+///
+/// ```toml
+/// [providers.openai.models."gpt-4o".hedge]
+/// delay_ms = 1500
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct HedgeConfig {
+    /// How long to wait for the primary before sending the hedged request.
+    pub delay_ms: u64,
 }
 
 /// Provider configurations container.
@@ -288,6 +328,15 @@ impl ProviderConfig {
     }
 
     fn validate(&self) -> Result<(), String> {
+        if self.max_response_bytes() == Some(0) {
+            return Err("max_response_bytes must be greater than 0".into());
+        }
+        if let Some(fair_queue) = self.fair_queue_config() {
+            fair_queue.validate()?;
+        }
+        if let Some(adaptive) = self.adaptive_concurrency_config() {
+            adaptive.validate()?;
+        }
         match self {
             Self::OpenAi(c) => c.validate(),
             Self::Anthropic(c) => c.validate(),
@@ -419,6 +468,81 @@ impl ProviderConfig {
         }
     }
 
+    /// Get fair queuing configuration for this provider, if enabled.
+    pub fn fair_queue_config(&self) -> Option<&FairQueueConfig> {
+        match self {
+            Self::OpenAi(c) => c.fair_queue.as_ref(),
+            Self::Anthropic(c) => c.fair_queue.as_ref(),
+            #[cfg(feature = "provider-bedrock")]
+            Self::Bedrock(c) => c.fair_queue.as_ref(),
+            #[cfg(feature = "provider-vertex")]
+            Self::Vertex(c) => c.fair_queue.as_ref(),
+            #[cfg(feature = "provider-azure")]
+            Self::AzureOpenAi(c) => c.fair_queue.as_ref(),
+            Self::Test(c) => c.fair_queue.as_ref(),
+        }
+    }
+
+    /// Get adaptive concurrency configuration for this provider, if enabled.
+    pub fn adaptive_concurrency_config(&self) -> Option<&AdaptiveConcurrencyConfig> {
+        match self {
+            Self::OpenAi(c) => c.adaptive_concurrency.as_ref(),
+            Self::Anthropic(c) => c.adaptive_concurrency.as_ref(),
+            #[cfg(feature = "provider-bedrock")]
+            Self::Bedrock(c) => c.adaptive_concurrency.as_ref(),
+            #[cfg(feature = "provider-vertex")]
+            Self::Vertex(c) => c.adaptive_concurrency.as_ref(),
+            #[cfg(feature = "provider-azure")]
+            Self::AzureOpenAi(c) => c.adaptive_concurrency.as_ref(),
+            Self::Test(c) => c.adaptive_concurrency.as_ref(),
+        }
+    }
+
+    /// Mutable circuit breaker configuration, for applying runtime settings.
+    pub fn circuit_breaker_config_mut(&mut self) -> &mut CircuitBreakerConfig {
+        match self {
+            Self::OpenAi(c) => &mut c.circuit_breaker,
+            Self::Anthropic(c) => &mut c.circuit_breaker,
+            #[cfg(feature = "provider-bedrock")]
+            Self::Bedrock(c) => &mut c.circuit_breaker,
+            #[cfg(feature = "provider-vertex")]
+            Self::Vertex(c) => &mut c.circuit_breaker,
+            #[cfg(feature = "provider-azure")]
+            Self::AzureOpenAi(c) => &mut c.circuit_breaker,
+            Self::Test(c) => &mut c.circuit_breaker,
+        }
+    }
+
+    /// Mutable retry configuration, for applying runtime settings.
+    pub fn retry_config_mut(&mut self) -> &mut RetryConfig {
+        match self {
+            Self::OpenAi(c) => &mut c.retry,
+            Self::Anthropic(c) => &mut c.retry,
+            #[cfg(feature = "provider-bedrock")]
+            Self::Bedrock(c) => &mut c.retry,
+            #[cfg(feature = "provider-vertex")]
+            Self::Vertex(c) => &mut c.retry,
+            #[cfg(feature = "provider-azure")]
+            Self::AzureOpenAi(c) => &mut c.retry,
+            Self::Test(c) => &mut c.retry,
+        }
+    }
+
+    /// Mutable fair queuing configuration, for applying runtime settings.
+    pub fn fair_queue_config_mut(&mut self) -> Option<&mut FairQueueConfig> {
+        match self {
+            Self::OpenAi(c) => c.fair_queue.as_mut(),
+            Self::Anthropic(c) => c.fair_queue.as_mut(),
+            #[cfg(feature = "provider-bedrock")]
+            Self::Bedrock(c) => c.fair_queue.as_mut(),
+            #[cfg(feature = "provider-vertex")]
+            Self::Vertex(c) => c.fair_queue.as_mut(),
+            #[cfg(feature = "provider-azure")]
+            Self::AzureOpenAi(c) => c.fair_queue.as_mut(),
+            Self::Test(c) => c.fair_queue.as_mut(),
+        }
+    }
+
     /// Get fallback provider names for this provider.
     ///
     /// Fallback providers are tried in order when the primary provider fails
@@ -501,6 +625,63 @@ impl ProviderConfig {
         }
     }
 
+    /// Names of this provider's upstream endpoints, starting with
+    /// [`PRIMARY_ENDPOINT`]. Empty for providers without `endpoints`.
+    pub fn endpoint_names(&self) -> Vec<&str> {
+        let endpoints = match self {
+            Self::OpenAi(c) => &c.endpoints,
+            #[cfg(feature = "provider-vertex")]
+            Self::Vertex(c) => &c.endpoints,
+            #[cfg(feature = "provider-azure")]
+            Self::AzureOpenAi(c) => &c.endpoints,
+            _ => return Vec::new(),
+        };
+        if endpoints.is_empty() {
+            return Vec::new();
+        }
+        std::iter::once(PRIMARY_ENDPOINT)
+            .chain(endpoints.iter().map(|e| e.name.as_str()))
+            .collect()
+    }
+
+    /// This provider's config restricted to one endpoint, for checking the
+    /// health of each endpoint separately. Returns `None` for an unknown
+    /// endpoint.
+    pub fn for_endpoint(&self, name: &str) -> Option<Self> {
+        match self {
+            Self::OpenAi(c) => {
+                if name == PRIMARY_ENDPOINT {
+                    let mut config = c.clone();
+                    config.endpoints.clear();
+                    return Some(Self::OpenAi(config));
+                }
+                let endpoint = c.endpoints.iter().find(|e| e.name == name)?;
+                Some(Self::OpenAi(c.with_endpoint(endpoint)))
+            }
+            #[cfg(feature = "provider-vertex")]
+            Self::Vertex(c) => {
+                if name == PRIMARY_ENDPOINT {
+                    let mut config = c.clone();
+                    config.endpoints.clear();
+                    return Some(Self::Vertex(config));
+                }
+                let endpoint = c.endpoints.iter().find(|e| e.name == name)?;
+                Some(Self::Vertex(c.with_endpoint(endpoint)))
+            }
+            #[cfg(feature = "provider-azure")]
+            Self::AzureOpenAi(c) => {
+                if name == PRIMARY_ENDPOINT {
+                    let mut config = c.clone();
+                    config.endpoints.clear();
+                    return Some(Self::AzureOpenAi(config));
+                }
+                let endpoint = c.endpoints.iter().find(|e| e.name == name)?;
+                Some(Self::AzureOpenAi(c.with_endpoint(endpoint)))
+            }
+            _ => (name == PRIMARY_ENDPOINT).then(|| self.clone()),
+        }
+    }
+
     /// Get sovereignty metadata for this provider.
     pub fn sovereignty(&self) -> Option<&SovereigntyMetadata> {
         match self {
@@ -516,6 +697,25 @@ impl ProviderConfig {
         }
     }
 
+    /// Get the per-provider response size limit in bytes, if configured.
+    ///
+    /// Streams that exceed it are aborted mid-flight. When unset there is no
+    /// per-provider limit, though the global `server.max_response_body_bytes`
+    /// still applies to buffered responses.
+    pub fn max_response_bytes(&self) -> Option<usize> {
+        match self {
+            Self::OpenAi(c) => c.max_response_bytes,
+            Self::Anthropic(c) => c.max_response_bytes,
+            #[cfg(feature = "provider-bedrock")]
+            Self::Bedrock(c) => c.max_response_bytes,
+            #[cfg(feature = "provider-vertex")]
+            Self::Vertex(c) => c.max_response_bytes,
+            #[cfg(feature = "provider-azure")]
+            Self::AzureOpenAi(c) => c.max_response_bytes,
+            Self::Test(c) => c.max_response_bytes,
+        }
+    }
+
     /// Get the catalog provider ID override for this provider.
     pub fn catalog_provider(&self) -> Option<&str> {
         match self {
@@ -633,6 +833,14 @@ pub struct OpenAiProviderConfig {
     #[serde(default)]
     pub circuit_breaker: CircuitBreakerConfig,
 
+    /// Weighted fair queuing across tenants when the provider is at capacity.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fair_queue: Option<FairQueueConfig>,
+
+    /// Concurrency limit that adapts to upstream latency and 429s.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub adaptive_concurrency: Option<AdaptiveConcurrencyConfig>,
+
     /// Fallback providers to try when this provider fails.
     /// Providers are tried in order on retryable errors (5xx, timeout, circuit breaker open).
     #[serde(default)]
@@ -656,6 +864,18 @@ pub struct OpenAiProviderConfig {
     /// Sovereignty and compliance metadata for this provider.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sovereignty: Option<SovereigntyMetadata>,
+
+    /// Maximum response body size in bytes accepted from this provider.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_response_bytes: Option<usize>,
+
+    /// Additional upstream endpoints with their own `base_url`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub endpoints: Vec<ProviderEndpoint>,
+
+    /// How to pick an endpoint per request when `endpoints` is set.
+    #[serde(default)]
+    pub endpoint_selection: EndpointSelection,
 }
 
 impl OpenAiProviderConfig {
@@ -664,13 +884,28 @@ impl OpenAiProviderConfig {
         if self.base_url == default_openai_base_url() && self.api_key.is_none() {
             return Err("api_key is required for OpenAI's API".into());
         }
-        Ok(())
+        validate_endpoints(&self.endpoints, "base_url", |e| {
+            e.base_url.as_deref().is_some_and(|u| !u.is_empty())
+        })
     }
 
     /// Check if this is the native OpenAI API (not a compatible provider).
     pub fn is_native_openai(&self) -> bool {
         self.base_url.contains("api.openai.com")
     }
+
+    /// This config pointed at one of its `endpoints` instead of the primary.
+    pub fn with_endpoint(&self, endpoint: &ProviderEndpoint) -> Self {
+        let mut config = self.clone();
+        config.endpoints.clear();
+        if let Some(base_url) = &endpoint.base_url {
+            config.base_url = base_url.clone();
+        }
+        if endpoint.api_key.is_some() {
+            config.api_key = endpoint.api_key.clone();
+        }
+        config
+    }
 }
 
 impl std::fmt::Debug for OpenAiProviderConfig {
@@ -689,11 +924,14 @@ impl std::fmt::Debug for OpenAiProviderConfig {
             .field("models", &self.models)
             .field("retry", &self.retry)
             .field("circuit_breaker", &self.circuit_breaker)
+            .field("fair_queue", &self.fair_queue)
+            .field("adaptive_concurrency", &self.adaptive_concurrency)
             .field("fallback_providers", &self.fallback_providers)
             .field("model_fallbacks", &self.model_fallbacks)
             .field("health_check", &self.health_check)
             .field("catalog_provider", &self.catalog_provider)
             .field("sovereignty", &self.sovereignty)
+            .field("max_response_bytes", &self.max_response_bytes)
             .finish()
     }
 }
@@ -746,6 +984,14 @@ pub struct AnthropicProviderConfig {
     #[serde(default)]
     pub circuit_breaker: CircuitBreakerConfig,
 
+    /// Weighted fair queuing across tenants when the provider is at capacity.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fair_queue: Option<FairQueueConfig>,
+
+    /// Concurrency limit that adapts to upstream latency and 429s.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub adaptive_concurrency: Option<AdaptiveConcurrencyConfig>,
+
     /// Streaming buffer limits for DoS protection.
     #[serde(default)]
     pub streaming_buffer: StreamingBufferConfig,
@@ -771,6 +1017,10 @@ pub struct AnthropicProviderConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sovereignty: Option<SovereigntyMetadata>,
 
+    /// Maximum response body size in bytes accepted from this provider.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_response_bytes: Option<usize>,
+
     /// Models for which the `interleaved-thinking-2025-05-14` beta header
     /// should be sent when thinking is enabled. Each entry is matched against
     /// the model name as a substring (e.g. `"opus-4-6"` matches
@@ -810,6 +1060,10 @@ pub struct AnthropicProviderConfig {
     /// models gain support.
     #[serde(default = "default_mid_conversation_system_models")]
     pub mid_conversation_system_models: Vec<String>,
+
+    /// Automatic prompt caching of system prompts.
+    #[serde(default)]
+    pub prompt_caching: PromptCachingConfig,
 }
 
 pub fn default_interleaved_thinking_models() -> Vec<String> {
@@ -864,12 +1118,16 @@ impl std::fmt::Debug for AnthropicProviderConfig {
             .field("models", &self.models)
             .field("retry", &self.retry)
             .field("circuit_breaker", &self.circuit_breaker)
+            .field("fair_queue", &self.fair_queue)
+            .field("adaptive_concurrency", &self.adaptive_concurrency)
             .field("streaming_buffer", &self.streaming_buffer)
             .field("fallback_providers", &self.fallback_providers)
             .field("model_fallbacks", &self.model_fallbacks)
             .field("health_check", &self.health_check)
             .field("catalog_provider", &self.catalog_provider)
             .field("sovereignty", &self.sovereignty)
+            .field("max_response_bytes", &self.max_response_bytes)
+            .field("prompt_caching", &self.prompt_caching)
             .finish()
     }
 }
@@ -919,6 +1177,14 @@ pub struct BedrockProviderConfig {
     #[serde(default)]
     pub circuit_breaker: CircuitBreakerConfig,
 
+    /// Weighted fair queuing across tenants when the provider is at capacity.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fair_queue: Option<FairQueueConfig>,
+
+    /// Concurrency limit that adapts to upstream latency and 429s.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub adaptive_concurrency: Option<AdaptiveConcurrencyConfig>,
+
     /// Streaming buffer limits for DoS protection.
     #[serde(default)]
     pub streaming_buffer: StreamingBufferConfig,
@@ -950,6 +1216,10 @@ pub struct BedrockProviderConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sovereignty: Option<SovereigntyMetadata>,
 
+    /// Maximum response body size in bytes accepted from this provider.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_response_bytes: Option<usize>,
+
     /// Substring allowlist of Bedrock-hosted Claude models that should
     /// receive the `interleaved-thinking-2025-05-14` beta header when
     /// adaptive thinking is requested. Some Bedrock-hosted Claude models
@@ -958,6 +1228,11 @@ pub struct BedrockProviderConfig {
     /// Mirrors `AnthropicProviderConfig.interleaved_thinking_models`.
     #[serde(default = "default_interleaved_thinking_models")]
     pub interleaved_thinking_models: Vec<String>,
+
+    /// Automatic prompt caching of system prompts. Only enable for models
+    /// that support cache points (Claude and Nova); others reject them.
+    #[serde(default)]
+    pub prompt_caching: PromptCachingConfig,
 }
 
 #[cfg(feature = "provider-bedrock")]
@@ -966,13 +1241,16 @@ impl BedrockProviderConfig {
         if self.region.is_empty() {
             return Err("region cannot be empty".into());
         }
-        Ok(())
+        self.credentials.validate()
     }
 }
 
 #[cfg(feature = "provider-bedrock")]
 /// AWS credential configuration.
-#[derive(Clone, Default, Serialize, Deserialize)]
+///
+/// Temporary credentials (assumed roles, web identity, SSO profiles) are
+/// cached per process and refreshed five minutes before they expire.
+#[derive(Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[serde(deny_unknown_fields)]
@@ -989,11 +1267,31 @@ pub enum AwsCredentials {
         session_token: Option<String>,
     },
 
-    /// Assume an IAM role.
+    /// Assume an IAM role with STS, using the default credential chain to
+    /// call STS. For cross-account access, the role is in the other account.
     AssumeRole {
         role_arn: String,
+        /// External ID the role's trust policy requires, if any.
         #[serde(default)]
         external_id: Option<String>,
+        /// Role session name. Defaults to `hadrian`.
+        #[serde(default)]
+        session_name: Option<String>,
+        /// Session length in seconds (900 to 43200). Defaults to 3600.
+        #[serde(default)]
+        duration_secs: Option<u32>,
+    },
+
+    /// Exchange a web identity token for role credentials, as with EKS IAM
+    /// roles for service accounts (IRSA).
+    WebIdentity {
+        /// Role to assume. Defaults to `AWS_ROLE_ARN`.
+        #[serde(default)]
+        role_arn: Option<String>,
+        /// Path to the token file. Defaults to `AWS_WEB_IDENTITY_TOKEN_FILE`.
+        #[serde(default)]
+        token_file: Option<String>,
+        /// Role session name. Defaults to `hadrian`.
         #[serde(default)]
         session_name: Option<String>,
     },
@@ -1002,6 +1300,35 @@ pub enum AwsCredentials {
     Profile { name: String },
 }
 
+#[cfg(feature = "provider-bedrock")]
+impl AwsCredentials {
+    fn validate(&self) -> Result<(), String> {
+        match self {
+            Self::AssumeRole {
+                role_arn,
+                duration_secs,
+                ..
+            } => {
+                if role_arn.is_empty() {
+                    return Err("credentials.role_arn cannot be empty".into());
+                }
+                if duration_secs.is_some_and(|d| !(900..=43200).contains(&d)) {
+                    return Err("credentials.duration_secs must be between 900 and 43200".into());
+                }
+                Ok(())
+            }
+            Self::WebIdentity {
+                role_arn: Some(role_arn),
+                ..
+            } if role_arn.is_empty() => Err("credentials.role_arn cannot be empty".into()),
+            Self::Profile { name } if name.is_empty() => {
+                Err("credentials.name cannot be empty".into())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
 #[cfg(feature = "provider-bedrock")]
 impl std::fmt::Debug for AwsCredentials {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -1017,11 +1344,23 @@ impl std::fmt::Debug for AwsCredentials {
                 role_arn,
                 external_id,
                 session_name,
+                duration_secs,
             } => f
                 .debug_struct("AssumeRole")
                 .field("role_arn", role_arn)
                 .field("external_id", external_id)
                 .field("session_name", session_name)
+                .field("duration_secs", duration_secs)
+                .finish(),
+            AwsCredentials::WebIdentity {
+                role_arn,
+                token_file,
+                session_name,
+            } => f
+                .debug_struct("WebIdentity")
+                .field("role_arn", role_arn)
+                .field("token_file", token_file)
+                .field("session_name", session_name)
                 .finish(),
             AwsCredentials::Profile { name } => {
                 f.debug_struct("Profile").field("name", name).finish()
@@ -1071,9 +1410,20 @@ pub struct VertexProviderConfig {
 
     /// Model publisher. Defaults to "google".
     /// Use "anthropic" for Claude models, "meta" for Llama models on Vertex AI.
+    /// Models named `<publisher>/<model>`, and `claude-*` and `gemini-*`
+    /// models, go to their own publisher instead.
     #[serde(default = "default_vertex_publisher")]
     pub publisher: String,
 
+    /// Additional regions (or base URLs) to spread requests over or fail
+    /// over to. The provider's own `region` is the `primary` endpoint.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub endpoints: Vec<ProviderEndpoint>,
+
+    /// How to pick among `endpoints` for each request.
+    #[serde(default)]
+    pub endpoint_selection: EndpointSelection,
+
     /// Custom base URL override.
     /// Useful for VPC endpoints, testing, or custom deployments.
     /// If not specified, defaults based on auth mode:
@@ -1086,6 +1436,13 @@ pub struct VertexProviderConfig {
     #[serde(default)]
     pub credentials: GcpCredentials,
 
+    /// Service accounts to impersonate, in order, starting from
+    /// `credentials`. The last account makes the requests; earlier ones are
+    /// delegates that each hold `roles/iam.serviceAccountTokenCreator` on the
+    /// next. Ignored with api_key.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub impersonate_service_accounts: Vec<String>,
+
     /// Request timeout in seconds.
     #[serde(default = "default_timeout")]
     pub timeout_secs: u64,
@@ -1110,6 +1467,14 @@ pub struct VertexProviderConfig {
     #[serde(default)]
     pub circuit_breaker: CircuitBreakerConfig,
 
+    /// Weighted fair queuing across tenants when the provider is at capacity.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fair_queue: Option<FairQueueConfig>,
+
+    /// Concurrency limit that adapts to upstream latency and 429s.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub adaptive_concurrency: Option<AdaptiveConcurrencyConfig>,
+
     /// Streaming buffer limits for DoS protection.
     #[serde(default)]
     pub streaming_buffer: StreamingBufferConfig,
@@ -1134,24 +1499,54 @@ pub struct VertexProviderConfig {
     /// Sovereignty and compliance metadata for this provider.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sovereignty: Option<SovereigntyMetadata>,
+
+    /// Maximum response body size in bytes accepted from this provider.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_response_bytes: Option<usize>,
 }
 
 #[cfg(feature = "provider-vertex")]
 impl VertexProviderConfig {
     fn validate(&self) -> Result<(), String> {
         // Either api_key OR (project + region) must be provided
-        if self.api_key.is_some() {
-            // API key mode - project/region are optional (ignored)
-            Ok(())
-        } else {
+        if self.api_key.is_none() {
             // OAuth/ADC mode - project and region are required
             match (&self.project, &self.region) {
-                (Some(p), Some(r)) if !p.is_empty() && !r.is_empty() => Ok(()),
-                (Some(p), _) if p.is_empty() => Err("project cannot be empty".into()),
-                (_, Some(r)) if r.is_empty() => Err("region cannot be empty".into()),
-                _ => Err("either api_key or both project and region must be provided".into()),
+                (Some(p), Some(r)) if !p.is_empty() && !r.is_empty() => {}
+                (Some(p), _) if p.is_empty() => return Err("project cannot be empty".into()),
+                (_, Some(r)) if r.is_empty() => return Err("region cannot be empty".into()),
+                _ => {
+                    return Err("either api_key or both project and region must be provided".into());
+                }
             }
         }
+        if self
+            .impersonate_service_accounts
+            .iter()
+            .any(|account| !account.contains('@'))
+        {
+            return Err("impersonate_service_accounts must be service account emails".into());
+        }
+        validate_endpoints(&self.endpoints, "region or base_url", |e| {
+            e.region.as_deref().is_some_and(|r| !r.is_empty())
+                || e.base_url.as_deref().is_some_and(|u| !u.is_empty())
+        })
+    }
+
+    /// This config pointed at one of its `endpoints` instead of the primary.
+    pub fn with_endpoint(&self, endpoint: &ProviderEndpoint) -> Self {
+        let mut config = self.clone();
+        config.endpoints.clear();
+        if let Some(region) = &endpoint.region {
+            config.region = Some(region.clone());
+        }
+        if endpoint.base_url.is_some() {
+            config.base_url = endpoint.base_url.clone();
+        }
+        if endpoint.api_key.is_some() {
+            config.api_key = endpoint.api_key.clone();
+        }
+        config
     }
 
     /// Check if using API key authentication mode.
@@ -1168,20 +1563,29 @@ impl std::fmt::Debug for VertexProviderConfig {
             .field("project", &self.project)
             .field("region", &self.region)
             .field("publisher", &self.publisher)
+            .field("endpoints", &self.endpoints)
+            .field("endpoint_selection", &self.endpoint_selection)
             .field("base_url", &self.base_url)
             .field("credentials", &self.credentials)
+            .field(
+                "impersonate_service_accounts",
+                &self.impersonate_service_accounts,
+            )
             .field("timeout_secs", &self.timeout_secs)
             .field("allowed_models", &self.allowed_models)
             .field("model_aliases", &self.model_aliases)
             .field("models", &self.models)
             .field("retry", &self.retry)
             .field("circuit_breaker", &self.circuit_breaker)
+            .field("fair_queue", &self.fair_queue)
+            .field("adaptive_concurrency", &self.adaptive_concurrency)
             .field("streaming_buffer", &self.streaming_buffer)
             .field("fallback_providers", &self.fallback_providers)
             .field("model_fallbacks", &self.model_fallbacks)
             .field("health_check", &self.health_check)
             .field("catalog_provider", &self.catalog_provider)
             .field("sovereignty", &self.sovereignty)
+            .field("max_response_bytes", &self.max_response_bytes)
             .finish()
     }
 }
@@ -1254,6 +1658,14 @@ pub struct AzureOpenAiProviderConfig {
     #[serde(default)]
     pub circuit_breaker: CircuitBreakerConfig,
 
+    /// Weighted fair queuing across tenants when the provider is at capacity.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fair_queue: Option<FairQueueConfig>,
+
+    /// Concurrency limit that adapts to upstream latency and 429s.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub adaptive_concurrency: Option<AdaptiveConcurrencyConfig>,
+
     /// Fallback providers to try when this provider fails.
     #[serde(default)]
     pub fallback_providers: Vec<String>,
@@ -1274,6 +1686,19 @@ pub struct AzureOpenAiProviderConfig {
     /// Sovereignty and compliance metadata for this provider.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sovereignty: Option<SovereigntyMetadata>,
+
+    /// Maximum response body size in bytes accepted from this provider.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_response_bytes: Option<usize>,
+
+    /// Additional Azure resources, e.g. in other regions, with their own
+    /// `resource_name`. Deployments must exist under the same names in each.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub endpoints: Vec<ProviderEndpoint>,
+
+    /// How to pick an endpoint per request when `endpoints` is set.
+    #[serde(default)]
+    pub endpoint_selection: EndpointSelection,
 }
 
 #[cfg(feature = "provider-azure")]
@@ -1282,7 +1707,9 @@ impl AzureOpenAiProviderConfig {
         if self.resource_name.is_empty() {
             return Err("resource_name cannot be empty".into());
         }
-        Ok(())
+        validate_endpoints(&self.endpoints, "resource_name", |e| {
+            e.resource_name.as_deref().is_some_and(|r| !r.is_empty())
+        })
     }
 
     /// Get the base URL for this Azure OpenAI resource.
@@ -1290,6 +1717,21 @@ impl AzureOpenAiProviderConfig {
         format!("https://{}.openai.azure.com/openai", self.resource_name)
     }
 
+    /// This config pointed at one of its `endpoints` instead of the primary.
+    pub fn with_endpoint(&self, endpoint: &ProviderEndpoint) -> Self {
+        let mut config = self.clone();
+        config.endpoints.clear();
+        if let Some(resource_name) = &endpoint.resource_name {
+            config.resource_name = resource_name.clone();
+        }
+        if let Some(api_key) = &endpoint.api_key {
+            config.auth = AzureAuth::ApiKey {
+                api_key: api_key.clone(),
+            };
+        }
+        config
+    }
+
     /// Find a deployment by model name.
     pub fn deployment_for_model(&self, model: &str) -> Option<(&str, &AzureDeployment)> {
         self.deployments
@@ -1527,6 +1969,14 @@ impl RetryConfig {
         let target = self.read_only_max_retries.unwrap_or(READ_ONLY_MAX_RETRIES);
         self.with_max_retries(target)
     }
+
+    /// Get retry config for operations that must not run twice.
+    ///
+    /// Starting a fine-tuning job is billed per job, and a retry after a
+    /// timed-out attempt the provider did receive would start a second one.
+    pub fn without_retries(&self) -> std::borrow::Cow<'_, Self> {
+        self.with_max_retries(0)
+    }
 }
 
 fn default_max_retries() -> u32 {
@@ -1557,6 +2007,57 @@ fn default_true() -> bool {
     true
 }
 
+/// Automatic prompt caching for providers with explicit cache breakpoints
+/// (Anthropic and Bedrock).
+///
+/// With `auto_cache_system_prompt`, a system prompt of at least
+/// `min_system_prompt_chars` characters is marked as a cache breakpoint, so
+/// requests that share it are billed at the cached input rate. Requests that
+/// already carry `cache_control` anywhere are left alone, since the caller is
+/// managing breakpoints itself. Cache writes cost more than regular input, so
+/// only enable this for workloads that reuse their system prompts.
+///
+/// ```toml
+/// [providers.anthropic.prompt_caching]
+/// auto_cache_system_prompt = true
+/// min_system_prompt_chars = 4096
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct PromptCachingConfig {
+    /// Mark system prompts as cacheable when the request has no breakpoints.
+    #[serde(default)]
+    pub auto_cache_system_prompt: bool,
+
+    /// Minimum system prompt length, in characters, to mark as cacheable.
+    /// Providers don't cache prompts below roughly 1024 tokens.
+    /// Default: 4096
+    #[serde(default = "default_prompt_caching_min_system_prompt_chars")]
+    pub min_system_prompt_chars: usize,
+}
+
+impl Default for PromptCachingConfig {
+    fn default() -> Self {
+        Self {
+            auto_cache_system_prompt: false,
+            min_system_prompt_chars: default_prompt_caching_min_system_prompt_chars(),
+        }
+    }
+}
+
+impl PromptCachingConfig {
+    /// Whether a system prompt should be marked as a cache breakpoint.
+    pub fn caches_system_prompt(&self, system_prompt: &str) -> bool {
+        self.auto_cache_system_prompt
+            && system_prompt.chars().count() >= self.min_system_prompt_chars
+    }
+}
+
+fn default_prompt_caching_min_system_prompt_chars() -> usize {
+    4096
+}
+
 /// Configuration for streaming response buffer limits.
 ///
 /// These limits prevent DoS attacks from malformed SSE data or slow consumers.
@@ -1614,6 +2115,110 @@ fn default_max_output_buffer_chunks() -> usize {
     1000
 }
 
+/// An additional upstream endpoint for a provider, such as another Azure or
+/// Vertex AI region or a second deployment of an OpenAI-compatible server.
+///
+/// The provider's own `base_url`, `resource_name` or `region` is always the
+/// `primary` endpoint. Each endpoint has its own circuit breaker and latency tracking,
+/// and one is picked per request using the provider's `endpoint_selection`.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct ProviderEndpoint {
+    /// Endpoint name, shown in circuit breaker and health status as
+    /// `<provider>/<name>`.
+    pub name: String,
+
+    /// Base URL (OpenAI-compatible providers).
+    #[serde(default)]
+    pub base_url: Option<String>,
+
+    /// Azure resource name (Azure OpenAI providers).
+    #[serde(default)]
+    pub resource_name: Option<String>,
+
+    /// GCP region (Vertex AI providers).
+    #[serde(default)]
+    pub region: Option<String>,
+
+    /// API key for this endpoint. Defaults to the provider's key.
+    #[serde(default)]
+    pub api_key: Option<String>,
+
+    /// Priority for `endpoint_selection = "priority"`; lower is preferred.
+    /// The primary endpoint has priority 0.
+    #[serde(default)]
+    pub priority: u32,
+}
+
+impl std::fmt::Debug for ProviderEndpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProviderEndpoint")
+            .field("name", &self.name)
+            .field("base_url", &self.base_url)
+            .field("resource_name", &self.resource_name)
+            .field("region", &self.region)
+            .field("api_key", &self.api_key.as_ref().map(|_| "****"))
+            .field("priority", &self.priority)
+            .finish()
+    }
+}
+
+/// Name of the endpoint formed by a provider's own connection settings.
+pub const PRIMARY_ENDPOINT: &str = "primary";
+
+/// Validate endpoint names and check each endpoint sets `location`
+/// (`base_url` or `resource_name`) through `has_location`.
+fn validate_endpoints(
+    endpoints: &[ProviderEndpoint],
+    location: &str,
+    has_location: impl Fn(&ProviderEndpoint) -> bool,
+) -> Result<(), String> {
+    let mut seen = std::collections::HashSet::new();
+    for endpoint in endpoints {
+        if endpoint.name.is_empty() || endpoint.name.contains('/') {
+            return Err(format!(
+                "endpoint name '{}' must be non-empty and must not contain '/'",
+                endpoint.name
+            ));
+        }
+        if endpoint.name == PRIMARY_ENDPOINT {
+            return Err(format!(
+                "endpoint name '{PRIMARY_ENDPOINT}' is reserved for the provider's own settings"
+            ));
+        }
+        if !seen.insert(endpoint.name.as_str()) {
+            return Err(format!(
+                "endpoint '{}' is listed more than once",
+                endpoint.name
+            ));
+        }
+        if !has_location(endpoint) {
+            return Err(format!("endpoint '{}' must set {location}", endpoint.name));
+        }
+    }
+    Ok(())
+}
+
+/// How a provider with several endpoints picks one for each request.
+///
+/// Endpoints whose circuit breaker is open are skipped unless all of them
+/// are open.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum EndpointSelection {
+    /// The endpoint with the lowest priority value, in config order on ties.
+    /// Other endpoints are only used while it is unavailable.
+    #[default]
+    Priority,
+    /// Rotate through endpoints.
+    RoundRobin,
+    /// The endpoint with the lowest recent response latency. Endpoints
+    /// without latency samples yet are tried first.
+    LowestLatency,
+}
+
 /// Configuration for circuit breaker pattern on providers.
 ///
 /// The circuit breaker prevents hammering unhealthy providers by tracking failures
@@ -1641,6 +2246,13 @@ fn default_max_output_buffer_chunks() -> usize {
 /// - Fifth+ open: 300s (capped)
 ///
 /// The counter resets when the circuit successfully closes (provider recovers).
+///
+/// # Shared Mode
+///
+/// By default each gateway node tracks its own circuit. With `mode = "shared"`,
+/// a node that opens the circuit also records it in the cache, and other nodes
+/// pick it up and open theirs too, so with a Redis cache an outage found by one
+/// replica stops traffic from all of them.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(deny_unknown_fields)]
@@ -1678,11 +2290,27 @@ pub struct CircuitBreakerConfig {
     /// Caps the exponential backoff to prevent excessively long waits.
     #[serde(default = "default_max_open_timeout_secs")]
     pub max_open_timeout_secs: u64,
+
+    /// Whether the circuit is tracked per node or shared through the cache.
+    #[serde(default)]
+    pub mode: CircuitBreakerMode,
 }
 
-impl Default for CircuitBreakerConfig {
-    fn default() -> Self {
-        Self {
+/// Where circuit breaker state lives.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitBreakerMode {
+    /// Each node discovers failures on its own.
+    #[default]
+    Local,
+    /// Opens are published to the cache and applied on every node.
+    Shared,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
             enabled: false,
             failure_threshold: default_failure_threshold(),
             open_timeout_secs: default_open_timeout_secs(),
@@ -1690,6 +2318,7 @@ impl Default for CircuitBreakerConfig {
             failure_status_codes: default_circuit_breaker_failure_codes(),
             backoff_multiplier: default_backoff_multiplier(),
             max_open_timeout_secs: default_max_open_timeout_secs(),
+            mode: CircuitBreakerMode::default(),
         }
     }
 }
@@ -1735,6 +2364,293 @@ fn default_max_open_timeout_secs() -> u64 {
     300 // 5 minutes
 }
 
+// =============================================================================
+// Fair Queuing Configuration
+// =============================================================================
+
+/// Weighted fair queuing for a provider shared by many tenants.
+///
+/// Caps the number of requests in flight to the provider. While below the cap
+/// requests pass straight through; once it is reached, further requests queue
+/// and slots are handed out in weighted round-robin order across tenants
+/// rather than first come, first served. A tenant sending a burst only delays
+/// its own queued requests, not everyone else's.
+///
+/// Size `max_concurrent` a little under what the upstream account's rate limit
+/// sustains so queuing starts before the provider begins returning 429s.
+///
+/// Queued requests also have a priority. Higher priorities are always served
+/// first; tenants share slots fairly within a priority. A request's priority
+/// is its API key's tier (see `priority_tiers`), or lower if the client asks
+/// for less with an `x-priority` header.
+///
+/// ```toml
+/// [providers.openai.fair_queue]
+/// max_concurrent = 64
+/// key = "org"
+///
+/// [providers.openai.fair_queue.weights]
+/// "6f1c2a9e-0000-4000-8000-000000000001" = 4
+///
+/// [providers.openai.fair_queue.priority_tiers]
+/// "0b7e4d2a-0000-4000-8000-000000000002" = "high"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct FairQueueConfig {
+    /// Maximum requests in flight to this provider across all tenants.
+    /// Streaming requests hold their slot until the stream ends.
+    pub max_concurrent: u32,
+
+    /// How long a queued request waits for a slot before failing with
+    /// `reject_status`.
+    #[serde(default = "default_fair_queue_timeout_ms")]
+    pub queue_timeout_ms: u64,
+
+    /// Maximum requests waiting across all tenants. Requests beyond this are
+    /// rejected immediately.
+    #[serde(default = "default_fair_queue_max_depth")]
+    pub max_queue_depth: usize,
+
+    /// What counts as a tenant when sharing out slots.
+    #[serde(default)]
+    pub key: FairQueueKey,
+
+    /// Weight for tenants not listed in `weights`.
+    #[serde(default = "default_fair_queue_weight")]
+    pub default_weight: u32,
+
+    /// Per-tenant weights keyed by org or project ID (matching `key`). A
+    /// tenant with weight 4 gets four slots for every one given to a tenant
+    /// with weight 1 while both have requests queued.
+    #[serde(default)]
+    pub weights: HashMap<String, u32>,
+
+    /// Priority for API keys not listed in `priority_tiers`, and for requests
+    /// without an API key.
+    #[serde(default)]
+    pub default_priority: RequestPriority,
+
+    /// Priority tier per API key ID. A key's tier is the highest priority its
+    /// requests can have; the `x-priority` header can only lower it.
+    #[serde(default)]
+    pub priority_tiers: HashMap<String, RequestPriority>,
+
+    /// Status returned when a request times out in the queue or finds it
+    /// full: 503 (default) or 429.
+    #[serde(default = "default_fair_queue_reject_status")]
+    pub reject_status: u16,
+}
+
+impl FairQueueConfig {
+    fn validate(&self) -> Result<(), String> {
+        if self.max_concurrent == 0 {
+            return Err("fair_queue.max_concurrent must be greater than 0".into());
+        }
+        if !matches!(self.reject_status, 429 | 503) {
+            return Err(format!(
+                "fair_queue.reject_status must be 429 or 503, got {}",
+                self.reject_status
+            ));
+        }
+        if self.default_weight == 0 {
+            return Err("fair_queue.default_weight must be greater than 0".into());
+        }
+        if let Some((tenant, _)) = self.weights.iter().find(|(_, w)| **w == 0) {
+            return Err(format!(
+                "fair_queue.weights.{tenant} must be greater than 0"
+            ));
+        }
+        Ok(())
+    }
+
+    /// Weight for the given tenant key.
+    pub fn weight_for(&self, tenant: &str) -> u32 {
+        self.weights
+            .get(tenant)
+            .copied()
+            .unwrap_or(self.default_weight)
+    }
+
+    /// Priority tier for the given API key ID.
+    pub fn tier_for(&self, api_key_id: &str) -> RequestPriority {
+        self.priority_tiers
+            .get(api_key_id)
+            .copied()
+            .unwrap_or(self.default_priority)
+    }
+}
+
+/// Tenant granularity for fair queuing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum FairQueueKey {
+    /// Share slots between organizations.
+    #[default]
+    Org,
+    /// Share slots between projects. Requests without a project fall back
+    /// to their organization.
+    Project,
+}
+
+/// Priority of a request waiting in a fair queue.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum RequestPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+impl RequestPriority {
+    pub const ALL: [Self; 3] = [Self::Low, Self::Normal, Self::High];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Normal => "normal",
+            Self::High => "high",
+        }
+    }
+
+    /// Parse an `x-priority` header value (case-insensitive).
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|p| value.trim().eq_ignore_ascii_case(p.as_str()))
+    }
+}
+
+fn default_fair_queue_timeout_ms() -> u64 {
+    30_000
+}
+
+fn default_fair_queue_max_depth() -> usize {
+    1000
+}
+
+fn default_fair_queue_weight() -> u32 {
+    1
+}
+
+fn default_fair_queue_reject_status() -> u16 {
+    503
+}
+
+/// Adaptive concurrency limit for a provider.
+///
+/// Caps requests in flight to the provider at a limit that moves with the
+/// upstream's behaviour: it grows by about one per round of successful
+/// requests while the provider is busy, and is cut by `backoff_ratio` when the
+/// provider answers 429 or 503, times out, or its latency rises above
+/// `latency_tolerance` times the lowest latency seen recently. Latency is
+/// measured to the response headers, so for streaming requests it is the time
+/// to first byte.
+///
+/// Requests over the limit wait up to `queue_timeout_ms` for a slot and then
+/// fail with 503, which sends them down the fallback chain. Combine with
+/// `fair_queue` to share the slots that remain fairly between tenants.
+///
+/// ```toml
+/// [providers.openai.adaptive_concurrency]
+/// initial_limit = 32
+/// max_limit = 256
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct AdaptiveConcurrencyConfig {
+    /// Limit to start from.
+    #[serde(default = "default_adaptive_initial_limit")]
+    pub initial_limit: u32,
+
+    /// The limit never drops below this.
+    #[serde(default = "default_adaptive_min_limit")]
+    pub min_limit: u32,
+
+    /// The limit never grows above this.
+    #[serde(default = "default_adaptive_max_limit")]
+    pub max_limit: u32,
+
+    /// Factor the limit is multiplied by when the provider is overloaded.
+    #[serde(default = "default_adaptive_backoff_ratio")]
+    pub backoff_ratio: f64,
+
+    /// Latency above this multiple of the baseline counts as overload.
+    #[serde(default = "default_adaptive_latency_tolerance")]
+    pub latency_tolerance: f64,
+
+    /// How long a request waits for a slot before failing with 503.
+    #[serde(default = "default_adaptive_queue_timeout_ms")]
+    pub queue_timeout_ms: u64,
+}
+
+impl Default for AdaptiveConcurrencyConfig {
+    fn default() -> Self {
+        Self {
+            initial_limit: default_adaptive_initial_limit(),
+            min_limit: default_adaptive_min_limit(),
+            max_limit: default_adaptive_max_limit(),
+            backoff_ratio: default_adaptive_backoff_ratio(),
+            latency_tolerance: default_adaptive_latency_tolerance(),
+            queue_timeout_ms: default_adaptive_queue_timeout_ms(),
+        }
+    }
+}
+
+impl AdaptiveConcurrencyConfig {
+    fn validate(&self) -> Result<(), String> {
+        if self.min_limit == 0 {
+            return Err("adaptive_concurrency.min_limit must be greater than 0".into());
+        }
+        if self.min_limit > self.max_limit {
+            return Err("adaptive_concurrency.min_limit must not exceed max_limit".into());
+        }
+        if !(self.min_limit..=self.max_limit).contains(&self.initial_limit) {
+            return Err(
+                "adaptive_concurrency.initial_limit must be between min_limit and max_limit".into(),
+            );
+        }
+        if !(self.backoff_ratio > 0.0 && self.backoff_ratio < 1.0) {
+            return Err("adaptive_concurrency.backoff_ratio must be between 0 and 1".into());
+        }
+        if self.latency_tolerance <= 1.0 {
+            return Err("adaptive_concurrency.latency_tolerance must be greater than 1".into());
+        }
+        Ok(())
+    }
+}
+
+fn default_adaptive_initial_limit() -> u32 {
+    20
+}
+
+fn default_adaptive_min_limit() -> u32 {
+    1
+}
+
+fn default_adaptive_max_limit() -> u32 {
+    200
+}
+
+fn default_adaptive_backoff_ratio() -> f64 {
+    0.9
+}
+
+fn default_adaptive_latency_tolerance() -> f64 {
+    3.0
+}
+
+fn default_adaptive_queue_timeout_ms() -> u64 {
+    10_000
+}
+
 // =============================================================================
 // Provider Health Check Configuration
 // =============================================================================
@@ -1789,6 +2705,16 @@ pub enum ProviderHealthCheckMode {
 /// # Only for mode = "inference"
 /// model = "gpt-4o-mini"  # Cheap model for health checks
 /// prompt = "Say OK"      # Simple prompt (default: "ping")
+///
+/// # Shift traffic away while degraded
+/// [providers.my-openai.health_check.routing]
+/// enabled = true
+/// degraded_latency_ms = 5000
+///
+/// # Record canary completion latency and correctness
+/// [providers.my-openai.health_check.probe]
+/// models = ["gpt-4o-mini"]
+/// interval_secs = 300
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
@@ -1819,6 +2745,148 @@ pub struct ProviderHealthCheckConfig {
     /// Default: "ping"
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub prompt: Option<String>,
+
+    /// Adjust how much traffic the provider gets based on check results.
+    pub routing: HealthRoutingConfig,
+
+    /// Synthetic canary completions recorded as a latency history.
+    /// Default: none (no probes)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub probe: Option<ProviderProbeConfig>,
+}
+
+/// Default interval between provider probes in seconds.
+pub const DEFAULT_PROVIDER_PROBE_INTERVAL_SECS: u64 = 300;
+
+/// Default prompt for provider probes.
+pub const DEFAULT_PROVIDER_PROBE_PROMPT: &str = "Reply with the single word OK.";
+
+/// Default answer expected from provider probes.
+pub const DEFAULT_PROVIDER_PROBE_EXPECTED: &str = "OK";
+
+/// Scheduled canary completions for a provider.
+///
+/// Each run sends a tiny prompt to every model in `models` and records the
+/// end-to-end latency, token counts and whether the completion contained the
+/// expected answer. With a database, results are stored and returned as
+/// `probes` by `/admin/v1/providers/{name}/stats/history`. Probes are billed
+/// like any other request, so keep the interval long and `max_tokens` low.
+///
+/// Probes don't affect health status, circuit breakers or routing weights.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(default, deny_unknown_fields)]
+pub struct ProviderProbeConfig {
+    /// Models to probe.
+    /// Default: the health check `model`, or the provider's default
+    pub models: Vec<String>,
+
+    /// Interval between probe runs in seconds.
+    /// Default: 300 seconds
+    pub interval_secs: u64,
+
+    /// Prompt sent to each model.
+    /// Default: "Reply with the single word OK."
+    pub prompt: String,
+
+    /// Text the completion must contain (case-insensitive) to count as
+    /// correct.
+    /// Default: "OK"
+    pub expected: String,
+
+    /// Maximum output tokens per probe.
+    /// Default: 5
+    pub max_tokens: u64,
+
+    /// Days to keep stored probe results.
+    /// Default: 30
+    pub retention_days: u32,
+}
+
+impl Default for ProviderProbeConfig {
+    fn default() -> Self {
+        Self {
+            models: Vec::new(),
+            interval_secs: DEFAULT_PROVIDER_PROBE_INTERVAL_SECS,
+            prompt: DEFAULT_PROVIDER_PROBE_PROMPT.to_string(),
+            expected: DEFAULT_PROVIDER_PROBE_EXPECTED.to_string(),
+            max_tokens: 5,
+            retention_days: 30,
+        }
+    }
+}
+
+impl ProviderProbeConfig {
+    /// Get the probe interval as a Duration.
+    pub fn interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.interval_secs)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.interval_secs == 0 {
+            return Err("health_check.probe.interval_secs must be > 0".into());
+        }
+        if self.max_tokens == 0 {
+            return Err("health_check.probe.max_tokens must be > 0".into());
+        }
+        if self.prompt.trim().is_empty() {
+            return Err("health_check.probe.prompt must not be empty".into());
+        }
+        Ok(())
+    }
+}
+
+/// Health-based routing weight for a provider.
+///
+/// The weight is the share of requests routed to the provider while it has
+/// fallbacks configured; the rest go to its first fallback, with the provider
+/// kept as the last resort. A failed check drops the weight to 0, a check
+/// slower than `degraded_latency_ms` caps it at `degraded_weight`, and each
+/// healthy check restores `recovery_step` of it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(default, deny_unknown_fields)]
+pub struct HealthRoutingConfig {
+    /// Whether health checks adjust the provider's routing weight.
+    pub enabled: bool,
+
+    /// Check latency above which the provider counts as degraded.
+    /// Default: none (only failed checks reduce the weight)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub degraded_latency_ms: Option<u64>,
+
+    /// Highest weight while degraded, between 0 and 1.
+    /// Default: 0.25
+    pub degraded_weight: f64,
+
+    /// Weight restored per healthy check, between 0 and 1.
+    /// Default: 0.25
+    pub recovery_step: f64,
+}
+
+impl Default for HealthRoutingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            degraded_latency_ms: None,
+            degraded_weight: 0.25,
+            recovery_step: 0.25,
+        }
+    }
+}
+
+impl HealthRoutingConfig {
+    fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.degraded_weight) {
+            return Err("health_check.routing.degraded_weight must be between 0 and 1".into());
+        }
+        if !(self.recovery_step > 0.0 && self.recovery_step <= 1.0) {
+            return Err(
+                "health_check.routing.recovery_step must be greater than 0 and at most 1".into(),
+            );
+        }
+        Ok(())
+    }
 }
 
 impl Default for ProviderHealthCheckConfig {
@@ -1830,6 +2898,8 @@ impl Default for ProviderHealthCheckConfig {
             timeout_secs: DEFAULT_PROVIDER_HEALTH_CHECK_TIMEOUT_SECS,
             model: None,
             prompt: None,
+            routing: HealthRoutingConfig::default(),
+            probe: None,
         }
     }
 }
@@ -1859,7 +2929,10 @@ impl ProviderHealthCheckConfig {
         if self.enabled && self.mode == ProviderHealthCheckMode::Inference && self.model.is_none() {
             return Err("health_check.model is required when mode = \"inference\"".into());
         }
-        Ok(())
+        if let Some(probe) = &self.probe {
+            probe.validate()?;
+        }
+        self.routing.validate()
     }
 }
 
@@ -1993,6 +3066,14 @@ pub struct TestProviderConfig {
     #[serde(default)]
     pub circuit_breaker: CircuitBreakerConfig,
 
+    /// Weighted fair queuing across tenants when the provider is at capacity.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fair_queue: Option<FairQueueConfig>,
+
+    /// Concurrency limit that adapts to upstream latency and 429s.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub adaptive_concurrency: Option<AdaptiveConcurrencyConfig>,
+
     /// Fallback providers to try when this provider fails.
     #[serde(default)]
     pub fallback_providers: Vec<String>,
@@ -2013,6 +3094,10 @@ pub struct TestProviderConfig {
     /// Sovereignty and compliance metadata for this provider.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sovereignty: Option<SovereigntyMetadata>,
+
+    /// Maximum response body size in bytes accepted from this provider.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_response_bytes: Option<usize>,
 }
 
 impl TestProviderConfig {
@@ -2125,6 +3210,58 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "provider-bedrock")]
+    #[test]
+    fn test_parse_bedrock_web_identity() {
+        let config: ProvidersConfig = toml::from_str(
+            r#"
+            [bedrock]
+            type = "bedrock"
+            region = "us-east-1"
+
+            [bedrock.credentials]
+            type = "web_identity"
+            token_file = "/var/run/secrets/eks.amazonaws.com/serviceaccount/token"
+        "#,
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+
+        match config.get("bedrock").unwrap() {
+            ProviderConfig::Bedrock(c) => match &c.credentials {
+                AwsCredentials::WebIdentity {
+                    role_arn,
+                    token_file,
+                    ..
+                } => {
+                    assert!(role_arn.is_none());
+                    assert!(token_file.as_deref().unwrap().ends_with("/token"));
+                }
+                _ => panic!("Expected WebIdentity credentials"),
+            },
+            _ => panic!("Expected Bedrock provider"),
+        }
+    }
+
+    #[cfg(feature = "provider-bedrock")]
+    #[test]
+    fn test_validation_assume_role_duration() {
+        let config: ProvidersConfig = toml::from_str(
+            r#"
+            [bedrock]
+            type = "bedrock"
+            region = "us-east-1"
+
+            [bedrock.credentials]
+            type = "assume_role"
+            role_arn = "arn:aws:iam::123456789:role/bedrock-access"
+            duration_secs = 60
+        "#,
+        )
+        .unwrap();
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_parse_local_ollama() {
         let config: ProvidersConfig = toml::from_str(
@@ -2176,6 +3313,120 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "provider-azure")]
+    #[test]
+    fn test_parse_azure_openai_endpoints() {
+        let config: ProvidersConfig = toml::from_str(
+            r#"
+            [azure]
+            type = "azure_open_ai"
+            resource_name = "eastus-resource"
+            endpoint_selection = "lowest_latency"
+
+            [azure.auth]
+            type = "api_key"
+            api_key = "primary-key"
+
+            [[azure.endpoints]]
+            name = "westeurope"
+            resource_name = "westeurope-resource"
+            api_key = "westeurope-key"
+            priority = 1
+        "#,
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+
+        let provider = config.get("azure").unwrap();
+        assert_eq!(provider.endpoint_names(), ["primary", "westeurope"]);
+        match provider.for_endpoint("westeurope").unwrap() {
+            ProviderConfig::AzureOpenAi(c) => {
+                assert_eq!(c.endpoint_selection, EndpointSelection::LowestLatency);
+                assert_eq!(c.resource_name, "westeurope-resource");
+                assert!(c.endpoints.is_empty());
+                assert!(
+                    matches!(c.auth, AzureAuth::ApiKey { ref api_key } if api_key == "westeurope-key")
+                );
+            }
+            _ => panic!("Expected AzureOpenAi provider"),
+        }
+        assert!(provider.for_endpoint("missing").is_none());
+    }
+
+    #[test]
+    fn test_validation_endpoints() {
+        for (endpoints, valid) in [
+            (
+                r#"{ name = "eu", base_url = "https://eu.example.com/v1" }"#,
+                true,
+            ),
+            (r#"{ name = "eu" }"#, false),
+            (
+                r#"{ name = "primary", base_url = "https://eu.example.com/v1" }"#,
+                false,
+            ),
+            (
+                r#"{ name = "eu/west", base_url = "https://eu.example.com/v1" }"#,
+                false,
+            ),
+            (
+                r#"{ name = "eu", base_url = "https://a.example.com" }, { name = "eu", base_url = "https://b.example.com" }"#,
+                false,
+            ),
+        ] {
+            let config: ProvidersConfig = toml::from_str(&format!(
+                r#"
+                [local]
+                type = "open_ai"
+                base_url = "http://localhost:8000/v1"
+                endpoints = [{endpoints}]
+            "#
+            ))
+            .unwrap();
+            assert_eq!(config.validate().is_ok(), valid, "{endpoints}");
+        }
+    }
+
+    #[cfg(feature = "provider-vertex")]
+    #[test]
+    fn test_validation_vertex_impersonation_and_regions() {
+        let parse = |extra: &str| -> ProvidersConfig {
+            toml::from_str(&format!(
+                r#"
+                [vertex]
+                type = "vertex"
+                project = "my-project"
+                region = "us-east5"
+                {extra}
+            "#
+            ))
+            .unwrap()
+        };
+
+        let config = parse(
+            r#"
+            impersonate_service_accounts = ["vertex@my-project.iam.gserviceaccount.com"]
+            endpoints = [{ name = "europe", region = "europe-west1" }]
+            "#,
+        );
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.get("vertex").unwrap().endpoint_names(),
+            ["primary", "europe"]
+        );
+
+        assert!(
+            parse(r#"impersonate_service_accounts = ["vertex"]"#)
+                .validate()
+                .is_err()
+        );
+        assert!(
+            parse(r#"endpoints = [{ name = "europe" }]"#)
+                .validate()
+                .is_err()
+        );
+    }
+
     #[test]
     fn test_multiple_providers() {
         let config: ProvidersConfig = toml::from_str(
@@ -2463,6 +3714,106 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_fair_queue() {
+        let config: ProvidersConfig = toml::from_str(
+            r#"
+            [shared]
+            type = "open_ai"
+            api_key = "sk-xxx"
+
+            [shared.fair_queue]
+            max_concurrent = 8
+            key = "project"
+
+            [shared.fair_queue.weights]
+            "11111111-1111-1111-1111-111111111111" = 3
+        "#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+
+        let fq = config.get("shared").unwrap().fair_queue_config().unwrap();
+        assert_eq!(fq.max_concurrent, 8);
+        assert_eq!(fq.key, FairQueueKey::Project);
+        assert_eq!(fq.queue_timeout_ms, 30_000);
+        assert_eq!(fq.weight_for("11111111-1111-1111-1111-111111111111"), 3);
+        assert_eq!(fq.weight_for("someone-else"), 1);
+    }
+
+    #[test]
+    fn test_validation_fair_queue_rejects_zero_weight() {
+        let config: ProvidersConfig = toml::from_str(
+            r#"
+            [shared]
+            type = "open_ai"
+            api_key = "sk-xxx"
+            fair_queue = { max_concurrent = 8, weights = { "org-a" = 0 } }
+        "#,
+        )
+        .unwrap();
+
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("fair_queue.weights.org-a"));
+    }
+
+    #[test]
+    fn test_fair_queue_priority_tiers() {
+        let config: ProvidersConfig = toml::from_str(
+            r#"
+            [shared]
+            type = "open_ai"
+            api_key = "sk-xxx"
+            fair_queue = { max_concurrent = 8, default_priority = "low", reject_status = 429, priority_tiers = { "key-a" = "high" } }
+        "#,
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+
+        let fq = config.get("shared").unwrap().fair_queue_config().unwrap();
+        assert_eq!(fq.tier_for("key-a"), RequestPriority::High);
+        assert_eq!(fq.tier_for("key-b"), RequestPriority::Low);
+        assert_eq!(
+            RequestPriority::parse(" HIGH "),
+            Some(RequestPriority::High)
+        );
+        assert_eq!(RequestPriority::parse("urgent"), None);
+
+        let config: ProvidersConfig = toml::from_str(
+            r#"
+            [shared]
+            type = "open_ai"
+            api_key = "sk-xxx"
+            fair_queue = { max_concurrent = 8, reject_status = 500 }
+        "#,
+        )
+        .unwrap();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("fair_queue.reject_status"));
+    }
+
+    #[test]
+    fn test_circuit_breaker_mode() {
+        let config: ProvidersConfig = toml::from_str(
+            r#"
+            [local]
+            type = "open_ai"
+            api_key = "sk-xxx"
+            circuit_breaker = { enabled = true }
+
+            [shared]
+            type = "open_ai"
+            api_key = "sk-xxx"
+            circuit_breaker = { enabled = true, mode = "shared" }
+        "#,
+        )
+        .unwrap();
+
+        let mode = |name| config.get(name).unwrap().circuit_breaker_config().mode;
+        assert_eq!(mode("local"), CircuitBreakerMode::Local);
+        assert_eq!(mode("shared"), CircuitBreakerMode::Shared);
+    }
+
     #[test]
     fn test_validation_model_fallback_provider_not_found() {
         let config: ProvidersConfig = toml::from_str(
@@ -2923,11 +4274,16 @@ mod tests {
             models: HashMap::new(),
             retry: RetryConfig::default(),
             circuit_breaker: CircuitBreakerConfig::default(),
+            fair_queue: None,
+            adaptive_concurrency: None,
             fallback_providers: vec![],
             model_fallbacks: HashMap::new(),
             health_check: ProviderHealthCheckConfig::default(),
             catalog_provider: None,
             sovereignty: None,
+            max_response_bytes: None,
+            endpoints: vec![],
+            endpoint_selection: EndpointSelection::default(),
         };
 
         let debug_output = format!("{:?}", config);
@@ -2954,16 +4310,20 @@ mod tests {
             models: HashMap::new(),
             retry: RetryConfig::default(),
             circuit_breaker: CircuitBreakerConfig::default(),
+            fair_queue: None,
+            adaptive_concurrency: None,
             streaming_buffer: StreamingBufferConfig::default(),
             fallback_providers: vec![],
             model_fallbacks: HashMap::new(),
             health_check: ProviderHealthCheckConfig::default(),
             catalog_provider: None,
             sovereignty: None,
+            max_response_bytes: None,
             interleaved_thinking_models: default_interleaved_thinking_models(),
             adaptive_thinking_models: default_adaptive_thinking_models(),
             strict_thinking_models: default_strict_thinking_models(),
             mid_conversation_system_models: default_mid_conversation_system_models(),
+            prompt_caching: PromptCachingConfig::default(),
         };
 
         let debug_output = format!("{:?}", config);
@@ -3022,6 +4382,7 @@ mod tests {
             role_arn: "arn:aws:iam::123456789:role/MyRole".to_string(),
             external_id: Some("external-123".to_string()),
             session_name: None,
+            duration_secs: None,
         };
         let debug_output = format!("{:?}", creds);
         assert!(
@@ -3040,18 +4401,24 @@ mod tests {
             publisher: "google".to_string(),
             base_url: None,
             credentials: GcpCredentials::Default,
+            impersonate_service_accounts: Vec::new(),
+            endpoints: Vec::new(),
+            endpoint_selection: Default::default(),
             timeout_secs: 300,
             allowed_models: vec![],
             model_aliases: HashMap::new(),
             models: HashMap::new(),
             retry: RetryConfig::default(),
             circuit_breaker: CircuitBreakerConfig::default(),
+            fair_queue: None,
+            adaptive_concurrency: None,
             streaming_buffer: StreamingBufferConfig::default(),
             fallback_providers: vec![],
             model_fallbacks: HashMap::new(),
             health_check: ProviderHealthCheckConfig::default(),
             catalog_provider: None,
             sovereignty: None,
+            max_response_bytes: None,
         };
 
         let debug_output = format!("{:?}", config);
@@ -3306,6 +4673,7 @@ mod tests {
             timeout_secs: 10,
             model: Some("test-model".to_string()),
             prompt: Some("Hello".to_string()),
+            routing: HealthRoutingConfig::default(),
         };
 
         assert_eq!(config.interval(), std::time::Duration::from_secs(60));
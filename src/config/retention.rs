@@ -14,6 +14,7 @@
 //! usage_records_days = 90
 //! audit_logs_days = 730
 //! conversations_deleted_days = 30
+//! email_log_days = 30
 //!
 //! [retention.safety]
 //! dry_run = false
@@ -91,6 +92,12 @@ pub struct RetentionPeriods {
     /// Default: 30 days
     #[serde(default = "default_conversations_deleted_days")]
     pub conversations_deleted_days: u32,
+
+    /// Days to keep email send log entries.
+    /// The send log is only used for troubleshooting delivery.
+    /// Default: 30 days
+    #[serde(default = "default_email_log_days")]
+    pub email_log_days: u32,
 }
 
 impl Default for RetentionPeriods {
@@ -99,6 +106,7 @@ impl Default for RetentionPeriods {
             usage_records_days: default_usage_records_days(),
             audit_logs_days: default_audit_logs_days(),
             conversations_deleted_days: default_conversations_deleted_days(),
+            email_log_days: default_email_log_days(),
         }
     }
 }
@@ -115,6 +123,10 @@ fn default_conversations_deleted_days() -> u32 {
     30
 }
 
+fn default_email_log_days() -> u32 {
+    30
+}
+
 /// Safety settings for retention operations.
 ///
 /// These settings help prevent accidental data loss and allow
@@ -167,6 +179,7 @@ impl RetentionConfig {
         self.periods.usage_records_days > 0
             || self.periods.audit_logs_days > 0
             || self.periods.conversations_deleted_days > 0
+            || self.periods.email_log_days > 0
     }
 
     /// Get the interval as a Duration.
@@ -190,6 +203,11 @@ impl RetentionPeriods {
     pub fn should_retain_conversations(&self) -> bool {
         self.conversations_deleted_days > 0
     }
+
+    /// Check if email send log retention is enabled.
+    pub fn should_retain_email_log(&self) -> bool {
+        self.email_log_days > 0
+    }
 }
 
 #[cfg(test)]
@@ -255,11 +273,13 @@ mod tests {
             usage_records_days = 0
             audit_logs_days = 0
             conversations_deleted_days = 0
+            email_log_days = 0
         "#;
         let config: RetentionConfig = toml::from_str(toml).unwrap();
         assert!(!config.periods.should_retain_usage_records());
         assert!(!config.periods.should_retain_audit_logs());
         assert!(!config.periods.should_retain_conversations());
+        assert!(!config.periods.should_retain_email_log());
         assert!(!config.has_any_retention());
     }
 
@@ -271,6 +291,7 @@ mod tests {
         config.periods.usage_records_days = 0;
         config.periods.audit_logs_days = 0;
         config.periods.conversations_deleted_days = 0;
+        config.periods.email_log_days = 0;
         assert!(!config.has_any_retention());
 
         config.periods.usage_records_days = 30;
@@ -68,11 +68,25 @@ pub struct ServerConfig {
     #[serde(default = "default_streaming_idle_timeout")]
     pub streaming_idle_timeout_secs: u64,
 
+    /// Format of error response bodies, for both the data-plane and admin
+    /// APIs. Default: `openai`.
+    #[serde(default)]
+    pub error_format: ErrorFormat,
+
     /// TLS configuration. If omitted, serves plain HTTP.
-    /// In production, TLS is typically terminated at the load balancer.
+    /// In production, TLS is typically terminated at the load balancer unless
+    /// client certificate authentication (mTLS) is needed.
     #[serde(default)]
     pub tls: Option<TlsConfig>,
 
+    /// gRPC data-plane API, served on its own port.
+    #[serde(default)]
+    pub grpc: Option<GrpcConfig>,
+
+    /// GraphQL admin API at `/admin/graphql`.
+    #[serde(default)]
+    pub graphql: Option<GraphqlConfig>,
+
     /// Trusted proxy configuration for extracting real client IPs.
     #[serde(default)]
     pub trusted_proxies: TrustedProxiesConfig,
@@ -130,7 +144,10 @@ impl Default for ServerConfig {
             max_response_body_bytes: default_max_response_body(),
             timeout_secs: default_timeout(),
             streaming_idle_timeout_secs: default_streaming_idle_timeout(),
+            error_format: ErrorFormat::default(),
             tls: None,
+            grpc: None,
+            graphql: None,
             trusted_proxies: TrustedProxiesConfig::default(),
             cors: CorsConfig::default(),
             security_headers: SecurityHeadersConfig::default(),
@@ -197,6 +214,17 @@ pub struct ShutdownConfig {
     /// usage logging, etc.) to complete after the close signal.
     #[serde(default = "default_drain_secs")]
     pub drain_secs: u64,
+
+    /// Seconds to wait for in-flight data-plane requests (including streams)
+    /// once the instance is draining, before the server stops. Draining
+    /// starts on a shutdown signal or `POST /admin/v1/system/drain`.
+    #[serde(default = "default_drain_timeout_secs")]
+    pub drain_timeout_secs: u64,
+
+    /// `Retry-After` seconds sent with the 503 that turns away new requests
+    /// while draining.
+    #[serde(default = "default_drain_retry_after_secs")]
+    pub retry_after_secs: u64,
 }
 
 impl Default for ShutdownConfig {
@@ -204,6 +232,8 @@ impl Default for ShutdownConfig {
         Self {
             usage_buffer_flush_secs: default_usage_buffer_flush_secs(),
             drain_secs: default_drain_secs(),
+            drain_timeout_secs: default_drain_timeout_secs(),
+            retry_after_secs: default_drain_retry_after_secs(),
         }
     }
 }
@@ -216,15 +246,25 @@ fn default_drain_secs() -> u64 {
     30
 }
 
+fn default_drain_timeout_secs() -> u64 {
+    20
+}
+
+fn default_drain_retry_after_secs() -> u64 {
+    5
+}
+
 fn default_jwt_loader_concurrency() -> usize {
     10
 }
 
 /// TLS configuration.
 ///
-/// Native TLS termination is not yet implemented. Until it is, the gateway
-/// listens on plain HTTP and operators must terminate TLS upstream (reverse
-/// proxy / load balancer). Setting `[server.tls]` without
+/// Binaries built with the `tls` feature terminate TLS natively and can
+/// require or accept client certificates (mTLS), which authenticate as the
+/// service account they are mapped to. Without the feature the gateway
+/// listens on plain HTTP and TLS must be terminated upstream (reverse proxy /
+/// load balancer); in that build, setting `[server.tls]` without
 /// `acknowledge_unsupported = true` is treated as a misconfiguration and
 /// refuses startup, so an operator following stale documentation can't
 /// silently expose plaintext.
@@ -232,19 +272,166 @@ fn default_jwt_loader_concurrency() -> usize {
 #[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(deny_unknown_fields)]
 pub struct TlsConfig {
-    /// Path to the certificate file (PEM format).
+    /// Path to the certificate file (PEM format). May contain the full chain.
     pub cert_path: String,
 
     /// Path to the private key file (PEM format).
     pub key_path: String,
 
-    /// Set to `true` to acknowledge that native TLS termination is not yet
-    /// implemented and the gateway will continue to listen on plain HTTP.
-    /// When unset, the gateway refuses to start to avoid an operator
+    /// Set to `true` to acknowledge that this binary was built without the
+    /// `tls` feature and the gateway will continue to listen on plain HTTP.
+    /// When unset, such a binary refuses to start to avoid an operator
     /// accidentally exposing plaintext after copying TLS config from
-    /// stale documentation.
+    /// stale documentation. Ignored when native TLS is available.
     #[serde(default)]
     pub acknowledge_unsupported: bool,
+
+    /// Client certificate (mTLS) verification. When omitted, clients are not
+    /// asked for a certificate.
+    #[serde(default)]
+    pub client_auth: Option<ClientAuthConfig>,
+
+    /// Maximum time for a client to complete the TLS handshake before the
+    /// connection is dropped.
+    /// Default: 10 seconds
+    #[serde(default = "default_tls_handshake_timeout")]
+    pub handshake_timeout_secs: u64,
+}
+
+fn default_tls_handshake_timeout() -> u64 {
+    10
+}
+
+/// Format of error response bodies.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorFormat {
+    /// OpenAI's `{"error": {"type", "message", "param", "code"}}` shape.
+    #[default]
+    Openai,
+    /// RFC 9457 `application/problem+json` with `type`, `title`, `status`,
+    /// `detail` and `instance`, plus `code`, `category`, `request_id` and
+    /// (for upstream errors) `provider_error_code` extension members.
+    ProblemJson,
+}
+
+/// gRPC data-plane API.
+///
+/// Binaries built with the `grpc` feature serve the `hadrian.v1.Gateway`
+/// service (chat completions, unary and streaming, and embeddings) on a
+/// separate port. Calls are handed to the same routes as the HTTP API, so API
+/// key auth (from `authorization` or `x-api-key` metadata), limits,
+/// guardrails and usage logging all apply unchanged.
+///
+/// ```toml
+/// [server.grpc]
+/// port = 50051
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct GrpcConfig {
+    /// Host address to bind to. Defaults to `server.host`.
+    #[serde(default)]
+    pub host: Option<IpAddr>,
+
+    /// Port to listen on. Must differ from `server.port`.
+    /// Default: 50051
+    #[serde(default = "default_grpc_port")]
+    pub port: u16,
+}
+
+fn default_grpc_port() -> u16 {
+    50051
+}
+
+/// GraphQL admin API.
+///
+/// Binaries built with the `graphql` feature serve a read-only GraphQL
+/// endpoint at `/admin/graphql` covering organizations, projects, API keys,
+/// providers and usage. It sits behind the same admin authentication as the
+/// REST API, and every field runs the same authorization check as its REST
+/// counterpart.
+///
+/// ```toml
+/// [server.graphql]
+/// max_depth = 10
+/// max_complexity = 1000
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct GraphqlConfig {
+    /// Deepest selection nesting a query may use.
+    /// Default: 10
+    #[serde(default = "default_graphql_max_depth")]
+    pub max_depth: usize,
+
+    /// Highest complexity a query may have. Each field counts 1, and list
+    /// fields multiply their children by the requested `first`.
+    /// Default: 1000
+    #[serde(default = "default_graphql_max_complexity")]
+    pub max_complexity: usize,
+
+    /// Whether clients may introspect the schema.
+    /// Default: true
+    #[serde(default = "default_true")]
+    pub introspection: bool,
+}
+
+impl Default for GraphqlConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: default_graphql_max_depth(),
+            max_complexity: default_graphql_max_complexity(),
+            introspection: true,
+        }
+    }
+}
+
+fn default_graphql_max_depth() -> usize {
+    10
+}
+
+fn default_graphql_max_complexity() -> usize {
+    1000
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Client certificate verification for mutual TLS.
+///
+/// Presented certificates are verified against `ca_path`. A verified
+/// certificate is then matched against the client certificate mappings
+/// configured for service accounts (fingerprint, SAN or subject CN); a match
+/// authenticates the request as that service account.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct ClientAuthConfig {
+    /// Path to the CA bundle (PEM format) that client certificates must chain to.
+    pub ca_path: String,
+
+    /// Whether a client certificate is required to complete the handshake.
+    #[serde(default)]
+    pub mode: ClientCertMode,
+}
+
+/// Whether clients must present a certificate.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ClientCertMode {
+    /// Request a certificate but allow the handshake without one. Clients
+    /// without a certificate fall back to the configured auth mode
+    /// (API keys, SSO, ...). A certificate that is presented must still verify.
+    #[default]
+    Optional,
+    /// Reject the handshake unless the client presents a valid certificate.
+    Required,
 }
 
 /// Configuration for trusted reverse proxies.
@@ -949,4 +1136,30 @@ mod tests {
         assert!(config.http2_adaptive_window);
         assert_eq!(config.tcp_keepalive_secs, 60);
     }
+
+    #[test]
+    fn test_tls_config_parse_client_auth() {
+        let toml = r#"
+            cert_path = "/etc/ssl/gateway.crt"
+            key_path = "/etc/ssl/gateway.key"
+
+            [client_auth]
+            ca_path = "/etc/ssl/clients-ca.pem"
+        "#;
+        let config: TlsConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.handshake_timeout_secs, 10);
+        let client_auth = config.client_auth.unwrap();
+        assert_eq!(client_auth.ca_path, "/etc/ssl/clients-ca.pem");
+        assert_eq!(client_auth.mode, ClientCertMode::Optional);
+
+        let config: TlsConfig = toml::from_str(
+            r#"
+            cert_path = "a"
+            key_path = "b"
+            client_auth = { ca_path = "c", mode = "required" }
+        "#,
+        )
+        .unwrap();
+        assert_eq!(config.client_auth.unwrap().mode, ClientCertMode::Required);
+    }
 }
@@ -59,6 +59,12 @@ pub struct SovereigntyMetadata {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub inference_countries: Vec<String>,
 
+    /// Data residency region where requests are processed and stored
+    /// (e.g. "eu", "us", "apac"). Matched against organization data residency
+    /// policies during routing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+
     /// Compliance certifications and regulatory frameworks.
     /// Well-known values: "gdpr", "hipaa", "soc2", "soc2-type2", "iso27001",
     /// "fedramp", "fedramp-high", "pci-dss", "c5", "ismap", "hipaa-baa", "ccpa", "dpa"
@@ -110,6 +116,7 @@ impl SovereigntyMetadata {
                 } else {
                     m.inference_countries.clone()
                 },
+                region: m.region.clone().or_else(|| p.region.clone()),
                 certifications: if m.certifications.is_empty() {
                     p.certifications.clone()
                 } else {
@@ -138,6 +145,7 @@ impl SovereigntyMetadata {
     pub fn is_empty(&self) -> bool {
         self.hq_country.is_none()
             && self.inference_countries.is_empty()
+            && self.region.is_none()
             && self.certifications.is_empty()
             && self.on_prem.is_none()
             && self.trains_on_data.is_none()
@@ -179,6 +187,10 @@ pub struct SovereigntyRequirements {
     /// Only allow models with these licenses.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub allowed_licenses: Option<Vec<String>>,
+
+    /// Only allow providers/models whose `sovereignty.region` is in this list.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_regions: Option<Vec<String>>,
 }
 
 impl SovereigntyRequirements {
@@ -210,6 +222,7 @@ impl SovereigntyRequirements {
                     &b.blocked_hq_countries,
                 ),
                 allowed_licenses: merge_allowed_lists(&a.allowed_licenses, &b.allowed_licenses),
+                allowed_regions: merge_allowed_lists(&a.allowed_regions, &b.allowed_regions),
             }),
         }
     }
@@ -299,10 +312,56 @@ impl SovereigntyRequirements {
             }
         }
 
+        // Check data residency region
+        if let Some(allowed) = &self.allowed_regions {
+            match &sovereignty.region {
+                Some(region) if allowed.iter().any(|a| a.eq_ignore_ascii_case(region)) => {}
+                Some(region) => {
+                    return Err(format!(
+                        "provider region '{region}' violates data residency policy (allowed: {allowed:?})"
+                    ));
+                }
+                None => {
+                    return Err(format!(
+                        "provider region is unknown; cannot verify data residency (allowed: {allowed:?})"
+                    ));
+                }
+            }
+        }
+
         Ok(())
     }
 }
 
+/// Organization-level data residency policy.
+///
+/// Restricts every request made on behalf of the organization to providers
+/// and models tagged with one of the allowed regions. Stored on the
+/// organization and merged with API key and per-request sovereignty
+/// requirements at routing time.
+/// ```json
+/// { "allowed_regions": ["eu"] }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct DataResidencyPolicy {
+    /// Regions requests may be routed to, matched case-insensitively against
+    /// the provider/model `sovereignty.region` tag.
+    pub allowed_regions: Vec<String>,
+}
+
+impl DataResidencyPolicy {
+    /// Express this policy as sovereignty requirements so it can be merged
+    /// with API key and per-request requirements.
+    pub fn to_requirements(&self) -> SovereigntyRequirements {
+        SovereigntyRequirements {
+            allowed_regions: Some(self.allowed_regions.clone()),
+            ..Default::default()
+        }
+    }
+}
+
 /// For "allowed" lists, take the intersection if both are set, or whichever is set.
 /// Uses case-insensitive comparison to match `check()` behavior.
 fn merge_allowed_lists(a: &Option<Vec<String>>, b: &Option<Vec<String>>) -> Option<Vec<String>> {
@@ -779,4 +838,58 @@ mod tests {
         assert!(blocked.contains(&"CN".into()));
         assert!(blocked.contains(&"RU".into()));
     }
+
+    #[test]
+    fn test_requirements_check_region() {
+        let reqs = DataResidencyPolicy {
+            allowed_regions: vec!["eu".into()],
+        }
+        .to_requirements();
+        let eu = SovereigntyMetadata {
+            region: Some("EU".into()),
+            ..Default::default()
+        };
+        assert!(reqs.check(&eu, false).is_ok());
+
+        let us = SovereigntyMetadata {
+            region: Some("us".into()),
+            ..Default::default()
+        };
+        let err = reqs.check(&us, false).unwrap_err();
+        assert!(err.contains("data residency"), "{err}");
+
+        // Untagged providers cannot be proven compliant
+        let err = reqs
+            .check(&SovereigntyMetadata::default(), false)
+            .unwrap_err();
+        assert!(err.contains("unknown"), "{err}");
+    }
+
+    #[test]
+    fn test_merge_region_model_overrides_provider() {
+        let provider = SovereigntyMetadata {
+            region: Some("us".into()),
+            ..Default::default()
+        };
+        let model = SovereigntyMetadata {
+            region: Some("eu".into()),
+            ..Default::default()
+        };
+        let result = SovereigntyMetadata::merge(Some(&provider), Some(&model)).unwrap();
+        assert_eq!(result.region, Some("eu".into()));
+    }
+
+    #[test]
+    fn test_merge_requirements_intersection_regions() {
+        let org = DataResidencyPolicy {
+            allowed_regions: vec!["eu".into(), "uk".into()],
+        }
+        .to_requirements();
+        let key = SovereigntyRequirements {
+            allowed_regions: Some(vec!["EU".into()]),
+            ..Default::default()
+        };
+        let merged = SovereigntyRequirements::merge(Some(&org), Some(&key)).unwrap();
+        assert_eq!(merged.allowed_regions, Some(vec!["eu".into()]));
+    }
 }
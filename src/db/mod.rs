@@ -11,6 +11,8 @@ pub mod wasm_sqlite;
 pub mod tests;
 
 use std::sync::Arc;
+#[cfg(feature = "database-postgres")]
+use std::sync::atomic::{AtomicBool, Ordering};
 
 pub use error::{DbError, DbResult};
 pub use repos::*;
@@ -24,13 +26,33 @@ pub struct PgPoolPair {
     pub write: sqlx::PgPool,
     /// Optional read replica pool. If None, reads use the write pool.
     pub read: Option<sqlx::PgPool>,
+    /// Whether the replica is reachable and caught up. Reads fall back to
+    /// the write pool while it's false.
+    replica_healthy: AtomicBool,
 }
 
 #[cfg(feature = "database-postgres")]
 impl PgPoolPair {
-    /// Get the pool to use for read operations.
+    fn new(write: sqlx::PgPool, read: Option<sqlx::PgPool>) -> Self {
+        Self {
+            write,
+            read,
+            replica_healthy: AtomicBool::new(true),
+        }
+    }
+
+    /// Get the pool to use for read operations: the replica while it's
+    /// healthy, otherwise the primary.
     pub fn read_pool(&self) -> &sqlx::PgPool {
-        self.read.as_ref().unwrap_or(&self.write)
+        match &self.read {
+            Some(read) if self.replica_healthy() => read,
+            _ => &self.write,
+        }
+    }
+
+    /// Whether reads are going to the replica. Always false without one.
+    pub fn replica_healthy(&self) -> bool {
+        self.read.is_some() && self.replica_healthy.load(Ordering::Relaxed)
     }
 
     /// Get the pool to use for write operations.
@@ -70,8 +92,43 @@ struct CachedRepos {
     scim_group_mappings: Arc<dyn ScimGroupMappingRepo>,
     // Per-org RBAC policies
     org_rbac_policies: Arc<dyn OrgRbacPolicyRepo>,
+    // Per-org data-plane request policies
+    org_request_policies: Arc<dyn OrgRequestPolicyRepo>,
+    // Requests mirrored by shadow traffic rules
+    shadow_results: Arc<dyn ShadowResultRepo>,
+    // Findings from usage anomaly detection
+    usage_anomalies: Arc<dyn UsageAnomalyRepo>,
+    // Synthetic canary completions from provider health checks
+    provider_probes: Arc<dyn ProviderProbeRepo>,
+    provider_settings: Arc<dyn ProviderSettingsRepo>,
+    // Model catalog entries added through the admin API
+    model_catalog_entries: Arc<dyn ModelCatalogEntryRepo>,
+    // Fine-tuning jobs proxied through /v1/fine_tuning/jobs
+    fine_tuning_jobs: Arc<dyn FineTuningJobRepo>,
+    // Copies of gateway files uploaded to providers
+    provider_files: Arc<dyn ProviderFileRepo>,
+    // Agents and their runs
+    agents: Arc<dyn AgentRepo>,
+    // Custom HTTP tools registered through the admin API
+    http_tools: Arc<dyn HttpToolRepo>,
+    // Recurring prompts and their run history
+    scheduled_tasks: Arc<dyn ScheduledTaskRepo>,
+    // Provider-reported usage imported for reconciliation
+    provider_usage_imports: Arc<dyn ProviderUsageImportRepo>,
+    // Which node last ran each cluster-wide background job
+    job_leaders: Arc<dyn JobLeaderRepo>,
+    // Background job run history and paused jobs
+    job_runs: Arc<dyn JobRunRepo>,
     // Service accounts (machine identities)
     service_accounts: Arc<dyn ServiceAccountRepo>,
+    // Client certificate → service account mappings (mTLS)
+    client_cert_mappings: Arc<dyn ClientCertMappingRepo>,
+    // Org and team invitations
+    invitations: Arc<dyn InvitationRepo>,
+    // Notification email send attempts
+    email_log: Arc<dyn EmailLogRepo>,
+    // Time-boxed organization role elevations
+    role_elevations: Arc<dyn RoleElevationRepo>,
     // OAuth PKCE authorization codes
     oauth_authorization_codes: Arc<dyn OAuthAuthorizationCodeRepo>,
     // Persisted Responses API records
@@ -80,6 +137,18 @@ struct CachedRepos {
     response_events: Arc<dyn ResponseEventsRepo>,
     // Containers + container_files (shell-tool /mnt/data artifacts)
     containers: Arc<dyn ContainersRepo>,
+    // Scheduled report delivery history
+    scheduled_report_runs: Arc<dyn ReportRunRepo>,
+    // Vector store sync run history and per-document sync state
+    vector_store_syncs: Arc<dyn VectorStoreSyncRepo>,
+    // Satellite reports received by a federation hub
+    federation: Arc<dyn FederationRepo>,
+    // TOTP authenticators for admin step-up authentication
+    admin_totp: Arc<dyn AdminTotpRepo>,
+    // Idempotency-Key claims and their stored responses
+    idempotency_keys: Arc<dyn IdempotencyKeyRepo>,
+    // WebAuthn credentials for admin passkey sign-in
+    admin_passkeys: Arc<dyn AdminPasskeyRepo>,
     // Parked MCP tool calls waiting on `mcp_approval_response`. Only
     // present when the `mcp` cargo feature is enabled.
     #[cfg(feature = "mcp")]
@@ -124,6 +193,10 @@ pub enum DbPoolRef<'a> {
 pub struct DbPool {
     inner: PoolStorage,
     repos: CachedRepos,
+    /// Postgres repos that read from the primary, used instead of `repos`
+    /// while the read replica is unhealthy. Only set with a read replica.
+    #[cfg(feature = "database-postgres")]
+    primary_repos: Option<CachedRepos>,
 }
 
 impl DbPool {
@@ -159,13 +232,39 @@ impl DbPool {
             #[cfg(feature = "sso")]
             scim_group_mappings: Arc::new(sqlite::SqliteScimGroupMappingRepo::new(pool.clone())),
             org_rbac_policies: Arc::new(sqlite::SqliteOrgRbacPolicyRepo::new(pool.clone())),
+            org_request_policies: Arc::new(sqlite::SqliteOrgRequestPolicyRepo::new(pool.clone())),
+            shadow_results: Arc::new(sqlite::SqliteShadowResultRepo::new(pool.clone())),
+            usage_anomalies: Arc::new(sqlite::SqliteUsageAnomalyRepo::new(pool.clone())),
+            provider_probes: Arc::new(sqlite::SqliteProviderProbeRepo::new(pool.clone())),
+            provider_settings: Arc::new(sqlite::SqliteProviderSettingsRepo::new(pool.clone())),
+            model_catalog_entries: Arc::new(sqlite::SqliteModelCatalogEntryRepo::new(pool.clone())),
+            fine_tuning_jobs: Arc::new(sqlite::SqliteFineTuningJobRepo::new(pool.clone())),
+            provider_files: Arc::new(sqlite::SqliteProviderFileRepo::new(pool.clone())),
+            agents: Arc::new(sqlite::SqliteAgentRepo::new(pool.clone())),
+            http_tools: Arc::new(sqlite::SqliteHttpToolRepo::new(pool.clone())),
+            scheduled_tasks: Arc::new(sqlite::SqliteScheduledTaskRepo::new(pool.clone())),
+            provider_usage_imports: Arc::new(sqlite::SqliteProviderUsageImportRepo::new(
+                pool.clone(),
+            )),
+            job_leaders: Arc::new(sqlite::SqliteJobLeaderRepo::new(pool.clone())),
+            job_runs: Arc::new(sqlite::SqliteJobRunRepo::new(pool.clone())),
             service_accounts: Arc::new(sqlite::SqliteServiceAccountRepo::new(pool.clone())),
+            client_cert_mappings: Arc::new(sqlite::SqliteClientCertMappingRepo::new(pool.clone())),
+            invitations: Arc::new(sqlite::SqliteInvitationRepo::new(pool.clone())),
+            email_log: Arc::new(sqlite::SqliteEmailLogRepo::new(pool.clone())),
+            role_elevations: Arc::new(sqlite::SqliteRoleElevationRepo::new(pool.clone())),
             oauth_authorization_codes: Arc::new(sqlite::SqliteOAuthAuthorizationCodeRepo::new(
                 pool.clone(),
             )),
             responses: Arc::new(sqlite::SqliteResponsesRepo::new(pool.clone())),
             response_events: Arc::new(sqlite::SqliteResponseEventsRepo::new(pool.clone())),
             containers: Arc::new(sqlite::SqliteContainersRepo::new(pool.clone())),
+            scheduled_report_runs: Arc::new(sqlite::SqliteReportRunRepo::new(pool.clone())),
+            vector_store_syncs: Arc::new(sqlite::SqliteVectorStoreSyncRepo::new(pool.clone())),
+            federation: Arc::new(sqlite::SqliteFederationRepo::new(pool.clone())),
+            admin_totp: Arc::new(sqlite::SqliteAdminTotpRepo::new(pool.clone())),
+            idempotency_keys: Arc::new(sqlite::SqliteIdempotencyKeyRepo::new(pool.clone())),
+            admin_passkeys: Arc::new(sqlite::SqliteAdminPasskeyRepo::new(pool.clone())),
             #[cfg(feature = "mcp")]
             mcp_pending_approvals: Arc::new(sqlite::SqliteMcpPendingApprovalsRepo::new(
                 pool.clone(),
@@ -174,6 +273,8 @@ impl DbPool {
         DbPool {
             inner: PoolStorage::Sqlite(pool),
             repos,
+            #[cfg(feature = "database-postgres")]
+            primary_repos: None,
         }
     }
 
@@ -208,13 +309,39 @@ impl DbPool {
             #[cfg(feature = "sso")]
             scim_group_mappings: unreachable!("SSO not supported in WASM builds"),
             org_rbac_policies: Arc::new(sqlite::SqliteOrgRbacPolicyRepo::new(pool.clone())),
+            org_request_policies: Arc::new(sqlite::SqliteOrgRequestPolicyRepo::new(pool.clone())),
+            shadow_results: Arc::new(sqlite::SqliteShadowResultRepo::new(pool.clone())),
+            usage_anomalies: Arc::new(sqlite::SqliteUsageAnomalyRepo::new(pool.clone())),
+            provider_probes: Arc::new(sqlite::SqliteProviderProbeRepo::new(pool.clone())),
+            provider_settings: Arc::new(sqlite::SqliteProviderSettingsRepo::new(pool.clone())),
+            model_catalog_entries: Arc::new(sqlite::SqliteModelCatalogEntryRepo::new(pool.clone())),
+            fine_tuning_jobs: Arc::new(sqlite::SqliteFineTuningJobRepo::new(pool.clone())),
+            provider_files: Arc::new(sqlite::SqliteProviderFileRepo::new(pool.clone())),
+            agents: Arc::new(sqlite::SqliteAgentRepo::new(pool.clone())),
+            http_tools: Arc::new(sqlite::SqliteHttpToolRepo::new(pool.clone())),
+            scheduled_tasks: Arc::new(sqlite::SqliteScheduledTaskRepo::new(pool.clone())),
+            provider_usage_imports: Arc::new(sqlite::SqliteProviderUsageImportRepo::new(
+                pool.clone(),
+            )),
+            job_leaders: Arc::new(sqlite::SqliteJobLeaderRepo::new(pool.clone())),
+            job_runs: Arc::new(sqlite::SqliteJobRunRepo::new(pool.clone())),
             service_accounts: Arc::new(sqlite::SqliteServiceAccountRepo::new(pool.clone())),
+            client_cert_mappings: Arc::new(sqlite::SqliteClientCertMappingRepo::new(pool.clone())),
+            invitations: Arc::new(sqlite::SqliteInvitationRepo::new(pool.clone())),
+            email_log: Arc::new(sqlite::SqliteEmailLogRepo::new(pool.clone())),
+            role_elevations: Arc::new(sqlite::SqliteRoleElevationRepo::new(pool.clone())),
             oauth_authorization_codes: Arc::new(sqlite::SqliteOAuthAuthorizationCodeRepo::new(
                 pool.clone(),
             )),
             responses: Arc::new(sqlite::SqliteResponsesRepo::new(pool.clone())),
             response_events: Arc::new(sqlite::SqliteResponseEventsRepo::new(pool.clone())),
             containers: Arc::new(sqlite::SqliteContainersRepo::new(pool.clone())),
+            scheduled_report_runs: Arc::new(sqlite::SqliteReportRunRepo::new(pool.clone())),
+            vector_store_syncs: Arc::new(sqlite::SqliteVectorStoreSyncRepo::new(pool.clone())),
+            federation: Arc::new(sqlite::SqliteFederationRepo::new(pool.clone())),
+            admin_totp: Arc::new(sqlite::SqliteAdminTotpRepo::new(pool.clone())),
+            idempotency_keys: Arc::new(sqlite::SqliteIdempotencyKeyRepo::new(pool.clone())),
+            admin_passkeys: Arc::new(sqlite::SqliteAdminPasskeyRepo::new(pool.clone())),
             #[cfg(feature = "mcp")]
             mcp_pending_approvals: Arc::new(sqlite::SqliteMcpPendingApprovalsRepo::new(
                 pool.clone(),
@@ -223,135 +350,27 @@ impl DbPool {
         DbPool {
             inner: PoolStorage::WasmSqlite(pool),
             repos,
+            #[cfg(feature = "database-postgres")]
+            primary_repos: None,
         }
     }
 
     /// Create a DbPool from existing PostgreSQL pools.
-    /// Primarily useful for testing.
+    ///
+    /// With a read replica, reads go to the replica until
+    /// [`DbPool::check_replica`] finds it unreachable or lagging, and to the
+    /// primary until it recovers.
     #[cfg(feature = "database-postgres")]
     pub fn from_postgres(write_pool: sqlx::PgPool, read_pool: Option<sqlx::PgPool>) -> Self {
-        let repos = CachedRepos {
-            organizations: Arc::new(postgres::PostgresOrganizationRepo::new(
-                write_pool.clone(),
-                read_pool.clone(),
-            )),
-            projects: Arc::new(postgres::PostgresProjectRepo::new(
-                write_pool.clone(),
-                read_pool.clone(),
-            )),
-            users: Arc::new(postgres::PostgresUserRepo::new(
-                write_pool.clone(),
-                read_pool.clone(),
-            )),
-            api_keys: Arc::new(postgres::PostgresApiKeyRepo::new(
-                write_pool.clone(),
-                read_pool.clone(),
-            )),
-            providers: Arc::new(postgres::PostgresDynamicProviderRepo::new(
-                write_pool.clone(),
-                read_pool.clone(),
-            )),
-            usage: Arc::new(postgres::PostgresUsageRepo::new(
-                write_pool.clone(),
-                read_pool.clone(),
-            )),
-            model_pricing: Arc::new(postgres::PostgresModelPricingRepo::new(
-                write_pool.clone(),
-                read_pool.clone(),
-            )),
-            conversations: Arc::new(postgres::PostgresConversationRepo::new(
-                write_pool.clone(),
-                read_pool.clone(),
-            )),
-            audit_logs: Arc::new(postgres::PostgresAuditLogRepo::new(
-                write_pool.clone(),
-                read_pool.clone(),
-            )),
-            vector_stores: Arc::new(postgres::PostgresVectorStoresRepo::new(
-                write_pool.clone(),
-                read_pool.clone(),
-            )),
-            files: Arc::new(postgres::PostgresFilesRepo::new(
-                write_pool.clone(),
-                read_pool.clone(),
-            )),
-            teams: Arc::new(postgres::PostgresTeamRepo::new(
-                write_pool.clone(),
-                read_pool.clone(),
-            )),
-            templates: Arc::new(postgres::PostgresTemplateRepo::new(
-                write_pool.clone(),
-                read_pool.clone(),
-            )),
-            skills: Arc::new(postgres::PostgresSkillRepo::new(
-                write_pool.clone(),
-                read_pool.clone(),
-            )),
-            #[cfg(feature = "sso")]
-            sso_group_mappings: Arc::new(postgres::PostgresSsoGroupMappingRepo::new(
-                write_pool.clone(),
-                read_pool.clone(),
-            )),
-            #[cfg(feature = "sso")]
-            org_sso_configs: Arc::new(postgres::PostgresOrgSsoConfigRepo::new(
-                write_pool.clone(),
-                read_pool.clone(),
-            )),
-            #[cfg(feature = "sso")]
-            domain_verifications: Arc::new(postgres::PostgresDomainVerificationRepo::new(
-                write_pool.clone(),
-                read_pool.clone(),
-            )),
-            #[cfg(feature = "sso")]
-            scim_configs: Arc::new(postgres::PostgresOrgScimConfigRepo::new(
-                write_pool.clone(),
-                read_pool.clone(),
-            )),
-            #[cfg(feature = "sso")]
-            scim_user_mappings: Arc::new(postgres::PostgresScimUserMappingRepo::new(
-                write_pool.clone(),
-                read_pool.clone(),
-            )),
-            #[cfg(feature = "sso")]
-            scim_group_mappings: Arc::new(postgres::PostgresScimGroupMappingRepo::new(
-                write_pool.clone(),
-                read_pool.clone(),
-            )),
-            org_rbac_policies: Arc::new(postgres::PostgresOrgRbacPolicyRepo::new(
-                write_pool.clone(),
-                read_pool.clone(),
-            )),
-            service_accounts: Arc::new(postgres::PostgresServiceAccountRepo::new(
-                write_pool.clone(),
-                read_pool.clone(),
-            )),
-            oauth_authorization_codes: Arc::new(postgres::PostgresOAuthAuthorizationCodeRepo::new(
-                write_pool.clone(),
-                read_pool.clone(),
-            )),
-            responses: Arc::new(postgres::PostgresResponsesRepo::new(
-                write_pool.clone(),
-                read_pool.clone(),
-            )),
-            response_events: Arc::new(postgres::PostgresResponseEventsRepo::new(
-                write_pool.clone(),
-                read_pool.clone(),
-            )),
-            containers: Arc::new(postgres::PostgresContainersRepo::new(
-                write_pool.clone(),
-                read_pool.clone(),
-            )),
-            #[cfg(feature = "mcp")]
-            mcp_pending_approvals: Arc::new(postgres::PostgresMcpPendingApprovalsRepo::new(
-                write_pool.clone(),
-            )),
-        };
+        // Repos that read from the primary, swapped in while the replica is
+        // unhealthy.
+        let primary_repos = read_pool
+            .as_ref()
+            .map(|_| postgres_repos(&write_pool, None));
         DbPool {
-            inner: PoolStorage::Postgres(PgPoolPair {
-                write: write_pool,
-                read: read_pool,
-            }),
-            repos,
+            repos: postgres_repos(&write_pool, read_pool.as_ref()),
+            primary_repos,
+            inner: PoolStorage::Postgres(PgPoolPair::new(write_pool, read_pool)),
         }
     }
 
@@ -420,13 +439,49 @@ impl DbPool {
                         pool.clone(),
                     )),
                     org_rbac_policies: Arc::new(sqlite::SqliteOrgRbacPolicyRepo::new(pool.clone())),
+                    org_request_policies: Arc::new(sqlite::SqliteOrgRequestPolicyRepo::new(
+                        pool.clone(),
+                    )),
+                    shadow_results: Arc::new(sqlite::SqliteShadowResultRepo::new(pool.clone())),
+                    usage_anomalies: Arc::new(sqlite::SqliteUsageAnomalyRepo::new(pool.clone())),
+                    provider_probes: Arc::new(sqlite::SqliteProviderProbeRepo::new(pool.clone())),
+                    provider_settings: Arc::new(sqlite::SqliteProviderSettingsRepo::new(
+                        pool.clone(),
+                    )),
+                    model_catalog_entries: Arc::new(sqlite::SqliteModelCatalogEntryRepo::new(
+                        pool.clone(),
+                    )),
+                    fine_tuning_jobs: Arc::new(sqlite::SqliteFineTuningJobRepo::new(pool.clone())),
+                    provider_files: Arc::new(sqlite::SqliteProviderFileRepo::new(pool.clone())),
+                    agents: Arc::new(sqlite::SqliteAgentRepo::new(pool.clone())),
+                    http_tools: Arc::new(sqlite::SqliteHttpToolRepo::new(pool.clone())),
+                    scheduled_tasks: Arc::new(sqlite::SqliteScheduledTaskRepo::new(pool.clone())),
+                    provider_usage_imports: Arc::new(sqlite::SqliteProviderUsageImportRepo::new(
+                        pool.clone(),
+                    )),
+                    job_leaders: Arc::new(sqlite::SqliteJobLeaderRepo::new(pool.clone())),
+                    job_runs: Arc::new(sqlite::SqliteJobRunRepo::new(pool.clone())),
                     service_accounts: Arc::new(sqlite::SqliteServiceAccountRepo::new(pool.clone())),
+                    client_cert_mappings: Arc::new(sqlite::SqliteClientCertMappingRepo::new(
+                        pool.clone(),
+                    )),
+                    invitations: Arc::new(sqlite::SqliteInvitationRepo::new(pool.clone())),
+                    email_log: Arc::new(sqlite::SqliteEmailLogRepo::new(pool.clone())),
+                    role_elevations: Arc::new(sqlite::SqliteRoleElevationRepo::new(pool.clone())),
                     oauth_authorization_codes: Arc::new(
                         sqlite::SqliteOAuthAuthorizationCodeRepo::new(pool.clone()),
                     ),
                     responses: Arc::new(sqlite::SqliteResponsesRepo::new(pool.clone())),
                     response_events: Arc::new(sqlite::SqliteResponseEventsRepo::new(pool.clone())),
                     containers: Arc::new(sqlite::SqliteContainersRepo::new(pool.clone())),
+                    scheduled_report_runs: Arc::new(sqlite::SqliteReportRunRepo::new(pool.clone())),
+                    vector_store_syncs: Arc::new(sqlite::SqliteVectorStoreSyncRepo::new(
+                        pool.clone(),
+                    )),
+                    federation: Arc::new(sqlite::SqliteFederationRepo::new(pool.clone())),
+                    admin_totp: Arc::new(sqlite::SqliteAdminTotpRepo::new(pool.clone())),
+                    idempotency_keys: Arc::new(sqlite::SqliteIdempotencyKeyRepo::new(pool.clone())),
+                    admin_passkeys: Arc::new(sqlite::SqliteAdminPasskeyRepo::new(pool.clone())),
                     #[cfg(feature = "mcp")]
                     mcp_pending_approvals: Arc::new(sqlite::SqliteMcpPendingApprovalsRepo::new(
                         pool.clone(),
@@ -436,6 +491,8 @@ impl DbPool {
                 Ok(DbPool {
                     inner: PoolStorage::Sqlite(pool),
                     repos,
+                    #[cfg(feature = "database-postgres")]
+                    primary_repos: None,
                 })
             }
             #[cfg(feature = "database-postgres")]
@@ -465,139 +522,18 @@ impl DbPool {
                 };
                 let write_pool = pool_opts().connect_with(connect_opts(&cfg.url)?).await?;
 
+                // Connect to the replica lazily so an unreachable replica
+                // sends reads to the primary instead of failing startup.
                 let read_pool = if let Some(read_url) = &cfg.read_url {
                     tracing::info!("Configuring read replica pool");
-                    Some(pool_opts().connect_with(connect_opts(read_url)?).await?)
+                    Some(pool_opts().connect_lazy_with(connect_opts(read_url)?))
                 } else {
                     None
                 };
 
-                let repos = CachedRepos {
-                    organizations: Arc::new(postgres::PostgresOrganizationRepo::new(
-                        write_pool.clone(),
-                        read_pool.clone(),
-                    )),
-                    projects: Arc::new(postgres::PostgresProjectRepo::new(
-                        write_pool.clone(),
-                        read_pool.clone(),
-                    )),
-                    users: Arc::new(postgres::PostgresUserRepo::new(
-                        write_pool.clone(),
-                        read_pool.clone(),
-                    )),
-                    api_keys: Arc::new(postgres::PostgresApiKeyRepo::new(
-                        write_pool.clone(),
-                        read_pool.clone(),
-                    )),
-                    providers: Arc::new(postgres::PostgresDynamicProviderRepo::new(
-                        write_pool.clone(),
-                        read_pool.clone(),
-                    )),
-                    usage: Arc::new(postgres::PostgresUsageRepo::new(
-                        write_pool.clone(),
-                        read_pool.clone(),
-                    )),
-                    model_pricing: Arc::new(postgres::PostgresModelPricingRepo::new(
-                        write_pool.clone(),
-                        read_pool.clone(),
-                    )),
-                    conversations: Arc::new(postgres::PostgresConversationRepo::new(
-                        write_pool.clone(),
-                        read_pool.clone(),
-                    )),
-                    audit_logs: Arc::new(postgres::PostgresAuditLogRepo::new(
-                        write_pool.clone(),
-                        read_pool.clone(),
-                    )),
-                    vector_stores: Arc::new(postgres::PostgresVectorStoresRepo::new(
-                        write_pool.clone(),
-                        read_pool.clone(),
-                    )),
-                    files: Arc::new(postgres::PostgresFilesRepo::new(
-                        write_pool.clone(),
-                        read_pool.clone(),
-                    )),
-                    teams: Arc::new(postgres::PostgresTeamRepo::new(
-                        write_pool.clone(),
-                        read_pool.clone(),
-                    )),
-                    templates: Arc::new(postgres::PostgresTemplateRepo::new(
-                        write_pool.clone(),
-                        read_pool.clone(),
-                    )),
-                    skills: Arc::new(postgres::PostgresSkillRepo::new(
-                        write_pool.clone(),
-                        read_pool.clone(),
-                    )),
-                    #[cfg(feature = "sso")]
-                    sso_group_mappings: Arc::new(postgres::PostgresSsoGroupMappingRepo::new(
-                        write_pool.clone(),
-                        read_pool.clone(),
-                    )),
-                    #[cfg(feature = "sso")]
-                    org_sso_configs: Arc::new(postgres::PostgresOrgSsoConfigRepo::new(
-                        write_pool.clone(),
-                        read_pool.clone(),
-                    )),
-                    #[cfg(feature = "sso")]
-                    domain_verifications: Arc::new(postgres::PostgresDomainVerificationRepo::new(
-                        write_pool.clone(),
-                        read_pool.clone(),
-                    )),
-                    #[cfg(feature = "sso")]
-                    scim_configs: Arc::new(postgres::PostgresOrgScimConfigRepo::new(
-                        write_pool.clone(),
-                        read_pool.clone(),
-                    )),
-                    #[cfg(feature = "sso")]
-                    scim_user_mappings: Arc::new(postgres::PostgresScimUserMappingRepo::new(
-                        write_pool.clone(),
-                        read_pool.clone(),
-                    )),
-                    #[cfg(feature = "sso")]
-                    scim_group_mappings: Arc::new(postgres::PostgresScimGroupMappingRepo::new(
-                        write_pool.clone(),
-                        read_pool.clone(),
-                    )),
-                    org_rbac_policies: Arc::new(postgres::PostgresOrgRbacPolicyRepo::new(
-                        write_pool.clone(),
-                        read_pool.clone(),
-                    )),
-                    service_accounts: Arc::new(postgres::PostgresServiceAccountRepo::new(
-                        write_pool.clone(),
-                        read_pool.clone(),
-                    )),
-                    oauth_authorization_codes: Arc::new(
-                        postgres::PostgresOAuthAuthorizationCodeRepo::new(
-                            write_pool.clone(),
-                            read_pool.clone(),
-                        ),
-                    ),
-                    responses: Arc::new(postgres::PostgresResponsesRepo::new(
-                        write_pool.clone(),
-                        read_pool.clone(),
-                    )),
-                    response_events: Arc::new(postgres::PostgresResponseEventsRepo::new(
-                        write_pool.clone(),
-                        read_pool.clone(),
-                    )),
-                    containers: Arc::new(postgres::PostgresContainersRepo::new(
-                        write_pool.clone(),
-                        read_pool.clone(),
-                    )),
-                    #[cfg(feature = "mcp")]
-                    mcp_pending_approvals: Arc::new(
-                        postgres::PostgresMcpPendingApprovalsRepo::new(write_pool.clone()),
-                    ),
-                };
-
-                Ok(DbPool {
-                    inner: PoolStorage::Postgres(PgPoolPair {
-                        write: write_pool,
-                        read: read_pool,
-                    }),
-                    repos,
-                })
+                let pool = Self::from_postgres(write_pool, read_pool);
+                pool.check_replica(cfg.replica_max_lag_secs).await;
+                Ok(pool)
             }
         }
     }
@@ -638,140 +574,322 @@ impl DbPool {
         }
     }
 
+    /// The repos to hand out: those reading from the replica while it's
+    /// healthy, otherwise those reading from the primary.
+    fn repos(&self) -> &CachedRepos {
+        #[cfg(feature = "database-postgres")]
+        if let (Some(primary_repos), PoolStorage::Postgres(pools)) =
+            (&self.primary_repos, &self.inner)
+            && !pools.replica_healthy()
+        {
+            return primary_repos;
+        }
+        &self.repos
+    }
+
+    /// Check the read replica's reachability and replication lag, sending
+    /// reads to the primary while it's unreachable or more than
+    /// `max_lag_secs` behind (0 skips the lag check) and back once it
+    /// recovers. Returns whether the replica is healthy, or `None` without
+    /// one.
+    #[cfg(feature = "database-postgres")]
+    pub async fn check_replica(&self, max_lag_secs: u64) -> Option<bool> {
+        let pools = match &self.inner {
+            #[cfg(feature = "database-sqlite")]
+            PoolStorage::Sqlite(_) => return None,
+            PoolStorage::Postgres(pools) => pools,
+        };
+        let replica = pools.read.as_ref()?;
+
+        // A replica that has replayed everything it received isn't behind,
+        // however old its last transaction is.
+        let healthy = match sqlx::query_scalar::<_, f64>(
+            "SELECT (CASE
+                WHEN pg_last_wal_receive_lsn() = pg_last_wal_replay_lsn() THEN 0
+                ELSE COALESCE(EXTRACT(EPOCH FROM now() - pg_last_xact_replay_timestamp()), 0)
+            END)::float8",
+        )
+        .fetch_one(replica)
+        .await
+        {
+            Ok(lag_secs) => {
+                crate::observability::metrics::set_db_replica_lag(lag_secs);
+                let healthy = max_lag_secs == 0 || lag_secs <= max_lag_secs as f64;
+                if !healthy {
+                    tracing::debug!(lag_secs, max_lag_secs, "Read replica is lagging");
+                }
+                healthy
+            }
+            Err(e) => {
+                tracing::debug!(error = %e, "Read replica check failed");
+                false
+            }
+        };
+
+        let was_healthy = pools.replica_healthy.swap(healthy, Ordering::Relaxed);
+        if was_healthy && !healthy {
+            tracing::warn!("Read replica is unreachable or lagging; routing reads to the primary");
+        } else if !was_healthy && healthy {
+            tracing::info!("Read replica recovered; routing reads to it again");
+        }
+        crate::observability::metrics::set_db_replica_healthy(healthy);
+        Some(healthy)
+    }
+
     /// Get organization repository
     pub fn organizations(&self) -> Arc<dyn OrganizationRepo> {
-        Arc::clone(&self.repos.organizations)
+        Arc::clone(&self.repos().organizations)
     }
 
     /// Get project repository
     pub fn projects(&self) -> Arc<dyn ProjectRepo> {
-        Arc::clone(&self.repos.projects)
+        Arc::clone(&self.repos().projects)
     }
 
     /// Get user repository
     pub fn users(&self) -> Arc<dyn UserRepo> {
-        Arc::clone(&self.repos.users)
+        Arc::clone(&self.repos().users)
     }
 
     /// Get API key repository
     pub fn api_keys(&self) -> Arc<dyn ApiKeyRepo> {
-        Arc::clone(&self.repos.api_keys)
+        Arc::clone(&self.repos().api_keys)
     }
 
     /// Get dynamic provider repository
     pub fn providers(&self) -> Arc<dyn DynamicProviderRepo> {
-        Arc::clone(&self.repos.providers)
+        Arc::clone(&self.repos().providers)
     }
 
     /// Get usage repository
     pub fn usage(&self) -> Arc<dyn UsageRepo> {
-        Arc::clone(&self.repos.usage)
+        Arc::clone(&self.repos().usage)
     }
 
     /// Get model pricing repository
     pub fn model_pricing(&self) -> Arc<dyn ModelPricingRepo> {
-        Arc::clone(&self.repos.model_pricing)
+        Arc::clone(&self.repos().model_pricing)
     }
 
     /// Get conversation repository
     pub fn conversations(&self) -> Arc<dyn ConversationRepo> {
-        Arc::clone(&self.repos.conversations)
+        Arc::clone(&self.repos().conversations)
     }
 
     /// Get audit log repository
     pub fn audit_logs(&self) -> Arc<dyn AuditLogRepo> {
-        Arc::clone(&self.repos.audit_logs)
+        Arc::clone(&self.repos().audit_logs)
     }
 
     /// Get collections repository
     pub fn vector_stores(&self) -> Arc<dyn VectorStoresRepo> {
-        Arc::clone(&self.repos.vector_stores)
+        Arc::clone(&self.repos().vector_stores)
     }
 
     /// Get files repository (OpenAI Files API)
     pub fn files(&self) -> Arc<dyn FilesRepo> {
-        Arc::clone(&self.repos.files)
+        Arc::clone(&self.repos().files)
     }
 
     /// Get team repository
     pub fn teams(&self) -> Arc<dyn TeamRepo> {
-        Arc::clone(&self.repos.teams)
+        Arc::clone(&self.repos().teams)
     }
 
     /// Get template repository
     pub fn templates(&self) -> Arc<dyn TemplateRepo> {
-        Arc::clone(&self.repos.templates)
+        Arc::clone(&self.repos().templates)
     }
 
     /// Get skill repository
     pub fn skills(&self) -> Arc<dyn SkillRepo> {
-        Arc::clone(&self.repos.skills)
+        Arc::clone(&self.repos().skills)
     }
 
     /// Get SSO group mapping repository
     #[cfg(feature = "sso")]
     pub fn sso_group_mappings(&self) -> Arc<dyn SsoGroupMappingRepo> {
-        Arc::clone(&self.repos.sso_group_mappings)
+        Arc::clone(&self.repos().sso_group_mappings)
     }
 
     /// Get organization SSO config repository
     #[cfg(feature = "sso")]
     pub fn org_sso_configs(&self) -> Arc<dyn OrgSsoConfigRepo> {
-        Arc::clone(&self.repos.org_sso_configs)
+        Arc::clone(&self.repos().org_sso_configs)
     }
 
     /// Get domain verification repository
     #[cfg(feature = "sso")]
     pub fn domain_verifications(&self) -> Arc<dyn DomainVerificationRepo> {
-        Arc::clone(&self.repos.domain_verifications)
+        Arc::clone(&self.repos().domain_verifications)
     }
 
     /// Get organization SCIM config repository
     #[cfg(feature = "sso")]
     pub fn scim_configs(&self) -> Arc<dyn OrgScimConfigRepo> {
-        Arc::clone(&self.repos.scim_configs)
+        Arc::clone(&self.repos().scim_configs)
     }
 
     /// Get SCIM user mapping repository
     #[cfg(feature = "sso")]
     pub fn scim_user_mappings(&self) -> Arc<dyn ScimUserMappingRepo> {
-        Arc::clone(&self.repos.scim_user_mappings)
+        Arc::clone(&self.repos().scim_user_mappings)
     }
 
     /// Get SCIM group mapping repository
     #[cfg(feature = "sso")]
     pub fn scim_group_mappings(&self) -> Arc<dyn ScimGroupMappingRepo> {
-        Arc::clone(&self.repos.scim_group_mappings)
+        Arc::clone(&self.repos().scim_group_mappings)
     }
 
     /// Get organization RBAC policy repository
     pub fn org_rbac_policies(&self) -> Arc<dyn OrgRbacPolicyRepo> {
-        Arc::clone(&self.repos.org_rbac_policies)
+        Arc::clone(&self.repos().org_rbac_policies)
+    }
+
+    /// Get organization request policy repository
+    pub fn org_request_policies(&self) -> Arc<dyn OrgRequestPolicyRepo> {
+        Arc::clone(&self.repos().org_request_policies)
+    }
+
+    /// Get shadow traffic result repository
+    pub fn shadow_results(&self) -> Arc<dyn ShadowResultRepo> {
+        Arc::clone(&self.repos().shadow_results)
+    }
+
+    /// Get usage anomaly repository
+    pub fn usage_anomalies(&self) -> Arc<dyn UsageAnomalyRepo> {
+        Arc::clone(&self.repos().usage_anomalies)
+    }
+
+    /// Get provider probe result repository
+    pub fn provider_probes(&self) -> Arc<dyn ProviderProbeRepo> {
+        Arc::clone(&self.repos().provider_probes)
+    }
+
+    /// Get provider runtime settings repository
+    pub fn provider_settings(&self) -> Arc<dyn ProviderSettingsRepo> {
+        Arc::clone(&self.repos().provider_settings)
+    }
+
+    /// Get custom model catalog entry repository
+    pub fn model_catalog_entries(&self) -> Arc<dyn ModelCatalogEntryRepo> {
+        Arc::clone(&self.repos().model_catalog_entries)
+    }
+
+    /// Get fine-tuning job repository
+    pub fn fine_tuning_jobs(&self) -> Arc<dyn FineTuningJobRepo> {
+        Arc::clone(&self.repos().fine_tuning_jobs)
+    }
+
+    /// Get provider file copy repository
+    pub fn provider_files(&self) -> Arc<dyn ProviderFileRepo> {
+        Arc::clone(&self.repos().provider_files)
+    }
+
+    /// Get agent repository
+    pub fn agents(&self) -> Arc<dyn AgentRepo> {
+        Arc::clone(&self.repos().agents)
+    }
+
+    /// Get HTTP tool repository
+    pub fn http_tools(&self) -> Arc<dyn HttpToolRepo> {
+        Arc::clone(&self.repos().http_tools)
+    }
+
+    /// Get scheduled task repository
+    pub fn scheduled_tasks(&self) -> Arc<dyn ScheduledTaskRepo> {
+        Arc::clone(&self.repos().scheduled_tasks)
+    }
+
+    /// Get provider usage import repository (reconciliation)
+    pub fn provider_usage_imports(&self) -> Arc<dyn ProviderUsageImportRepo> {
+        Arc::clone(&self.repos().provider_usage_imports)
+    }
+
+    /// Get job leader repository
+    pub fn job_leaders(&self) -> Arc<dyn JobLeaderRepo> {
+        Arc::clone(&self.repos().job_leaders)
+    }
+
+    /// Get background job run repository
+    pub fn job_runs(&self) -> Arc<dyn JobRunRepo> {
+        Arc::clone(&self.repos().job_runs)
     }
 
     /// Get service account repository
     pub fn service_accounts(&self) -> Arc<dyn ServiceAccountRepo> {
-        Arc::clone(&self.repos.service_accounts)
+        Arc::clone(&self.repos().service_accounts)
+    }
+
+    /// Get client certificate mapping repository
+    pub fn client_cert_mappings(&self) -> Arc<dyn ClientCertMappingRepo> {
+        Arc::clone(&self.repos().client_cert_mappings)
+    }
+
+    /// Get invitation repository
+    pub fn invitations(&self) -> Arc<dyn InvitationRepo> {
+        Arc::clone(&self.repos().invitations)
+    }
+
+    /// Get email send log repository
+    pub fn email_log(&self) -> Arc<dyn EmailLogRepo> {
+        Arc::clone(&self.repos().email_log)
+    }
+
+    /// Get role elevation repository
+    pub fn role_elevations(&self) -> Arc<dyn RoleElevationRepo> {
+        Arc::clone(&self.repos().role_elevations)
     }
 
     /// Get OAuth PKCE authorization code repository
     pub fn oauth_authorization_codes(&self) -> Arc<dyn OAuthAuthorizationCodeRepo> {
-        Arc::clone(&self.repos.oauth_authorization_codes)
+        Arc::clone(&self.repos().oauth_authorization_codes)
+    }
+
+    /// Get scheduled report run history repository
+    pub fn scheduled_report_runs(&self) -> Arc<dyn ReportRunRepo> {
+        Arc::clone(&self.repos().scheduled_report_runs)
+    }
+
+    /// Get vector store sync repository
+    pub fn vector_store_syncs(&self) -> Arc<dyn VectorStoreSyncRepo> {
+        Arc::clone(&self.repos().vector_store_syncs)
+    }
+
+    /// Get federation hub repository (satellite reports)
+    pub fn federation(&self) -> Arc<dyn FederationRepo> {
+        Arc::clone(&self.repos().federation)
+    }
+
+    /// Get admin TOTP enrollment repository (step-up authentication)
+    pub fn admin_totp(&self) -> Arc<dyn AdminTotpRepo> {
+        Arc::clone(&self.repos().admin_totp)
+    }
+
+    /// Get idempotency key repository
+    pub fn idempotency_keys(&self) -> Arc<dyn IdempotencyKeyRepo> {
+        Arc::clone(&self.repos().idempotency_keys)
+    }
+
+    /// Get admin passkey repository (WebAuthn credentials)
+    pub fn admin_passkeys(&self) -> Arc<dyn AdminPasskeyRepo> {
+        Arc::clone(&self.repos().admin_passkeys)
     }
 
     /// Get persisted Responses API record repository.
     pub fn responses(&self) -> Arc<dyn ResponsesRepo> {
-        Arc::clone(&self.repos.responses)
+        Arc::clone(&self.repos().responses)
     }
 
     /// Get the response event log repository.
     pub fn response_events(&self) -> Arc<dyn ResponseEventsRepo> {
-        Arc::clone(&self.repos.response_events)
+        Arc::clone(&self.repos().response_events)
     }
 
     /// Get the containers + container_files repository.
     pub fn containers(&self) -> Arc<dyn ContainersRepo> {
-        Arc::clone(&self.repos.containers)
+        Arc::clone(&self.repos().containers)
     }
 
     /// Get the MCP pending-approvals repository. Used by the
@@ -779,7 +897,7 @@ impl DbPool {
     /// resume them.
     #[cfg(feature = "mcp")]
     pub fn mcp_pending_approvals(&self) -> Arc<dyn McpPendingApprovalsRepo> {
-        Arc::clone(&self.repos.mcp_pending_approvals)
+        Arc::clone(&self.repos().mcp_pending_approvals)
     }
 
     /// Get a reference to the underlying database pool.
@@ -812,6 +930,17 @@ impl DbPool {
         }
     }
 
+    /// Get the SQLite pool if using SQLite.
+    /// Returns None for PostgreSQL databases.
+    #[cfg(feature = "database-sqlite")]
+    pub fn sqlite_pool(&self) -> Option<&sqlx::SqlitePool> {
+        match &self.inner {
+            PoolStorage::Sqlite(pool) => Some(pool),
+            #[cfg(feature = "database-postgres")]
+            PoolStorage::Postgres(_) => None,
+        }
+    }
+
     /// Health check for database connectivity
     pub async fn health_check(&self) -> DbResult<()> {
         match &self.inner {
@@ -822,11 +951,9 @@ impl DbPool {
             }
             #[cfg(feature = "database-postgres")]
             PoolStorage::Postgres(pools) => {
-                // Check both write and read pools
+                // Only the primary is required: reads fall back to it while
+                // the replica is down.
                 sqlx::query("SELECT 1").execute(&pools.write).await?;
-                if let Some(read) = &pools.read {
-                    sqlx::query("SELECT 1").execute(read).await?;
-                }
                 Ok(())
             }
             #[cfg(feature = "database-wasm-sqlite")]
@@ -843,3 +970,213 @@ impl DbPool {
         }
     }
 }
+
+/// Build the Postgres repos. Without a read pool, reads use the write pool.
+#[cfg(feature = "database-postgres")]
+fn postgres_repos(write_pool: &sqlx::PgPool, read_pool: Option<&sqlx::PgPool>) -> CachedRepos {
+    CachedRepos {
+        organizations: Arc::new(postgres::PostgresOrganizationRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        projects: Arc::new(postgres::PostgresProjectRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        users: Arc::new(postgres::PostgresUserRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        api_keys: Arc::new(postgres::PostgresApiKeyRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        providers: Arc::new(postgres::PostgresDynamicProviderRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        usage: Arc::new(postgres::PostgresUsageRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        model_pricing: Arc::new(postgres::PostgresModelPricingRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        conversations: Arc::new(postgres::PostgresConversationRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        audit_logs: Arc::new(postgres::PostgresAuditLogRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        vector_stores: Arc::new(postgres::PostgresVectorStoresRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        files: Arc::new(postgres::PostgresFilesRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        teams: Arc::new(postgres::PostgresTeamRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        templates: Arc::new(postgres::PostgresTemplateRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        skills: Arc::new(postgres::PostgresSkillRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        #[cfg(feature = "sso")]
+        sso_group_mappings: Arc::new(postgres::PostgresSsoGroupMappingRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        #[cfg(feature = "sso")]
+        org_sso_configs: Arc::new(postgres::PostgresOrgSsoConfigRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        #[cfg(feature = "sso")]
+        domain_verifications: Arc::new(postgres::PostgresDomainVerificationRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        #[cfg(feature = "sso")]
+        scim_configs: Arc::new(postgres::PostgresOrgScimConfigRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        #[cfg(feature = "sso")]
+        scim_user_mappings: Arc::new(postgres::PostgresScimUserMappingRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        #[cfg(feature = "sso")]
+        scim_group_mappings: Arc::new(postgres::PostgresScimGroupMappingRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        org_rbac_policies: Arc::new(postgres::PostgresOrgRbacPolicyRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        org_request_policies: Arc::new(postgres::PostgresOrgRequestPolicyRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        shadow_results: Arc::new(postgres::PostgresShadowResultRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        usage_anomalies: Arc::new(postgres::PostgresUsageAnomalyRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        provider_probes: Arc::new(postgres::PostgresProviderProbeRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        provider_settings: Arc::new(postgres::PostgresProviderSettingsRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        model_catalog_entries: Arc::new(postgres::PostgresModelCatalogEntryRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        fine_tuning_jobs: Arc::new(postgres::PostgresFineTuningJobRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        provider_files: Arc::new(postgres::PostgresProviderFileRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        agents: Arc::new(postgres::PostgresAgentRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        http_tools: Arc::new(postgres::PostgresHttpToolRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        scheduled_tasks: Arc::new(postgres::PostgresScheduledTaskRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        provider_usage_imports: Arc::new(postgres::PostgresProviderUsageImportRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        job_leaders: Arc::new(postgres::PostgresJobLeaderRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        job_runs: Arc::new(postgres::PostgresJobRunRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        service_accounts: Arc::new(postgres::PostgresServiceAccountRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        client_cert_mappings: Arc::new(postgres::PostgresClientCertMappingRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        invitations: Arc::new(postgres::PostgresInvitationRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        email_log: Arc::new(postgres::PostgresEmailLogRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        role_elevations: Arc::new(postgres::PostgresRoleElevationRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        oauth_authorization_codes: Arc::new(postgres::PostgresOAuthAuthorizationCodeRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        responses: Arc::new(postgres::PostgresResponsesRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        response_events: Arc::new(postgres::PostgresResponseEventsRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        containers: Arc::new(postgres::PostgresContainersRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        scheduled_report_runs: Arc::new(postgres::PostgresReportRunRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        vector_store_syncs: Arc::new(postgres::PostgresVectorStoreSyncRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        federation: Arc::new(postgres::PostgresFederationRepo::new(
+            write_pool.clone(),
+            read_pool.cloned(),
+        )),
+        admin_totp: Arc::new(postgres::PostgresAdminTotpRepo::new(write_pool.clone())),
+        idempotency_keys: Arc::new(postgres::PostgresIdempotencyKeyRepo::new(
+            write_pool.clone(),
+        )),
+        admin_passkeys: Arc::new(postgres::PostgresAdminPasskeyRepo::new(write_pool.clone())),
+        #[cfg(feature = "mcp")]
+        mcp_pending_approvals: Arc::new(postgres::PostgresMcpPendingApprovalsRepo::new(
+            write_pool.clone(),
+        )),
+    }
+}
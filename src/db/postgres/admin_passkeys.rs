@@ -0,0 +1,130 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::{PgPool, Row, postgres::PgRow};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{AdminPasskeyRepo, truncate_to_millis},
+    },
+    models::{AdminPasskey, CreateAdminPasskey},
+};
+
+pub struct PostgresAdminPasskeyRepo {
+    write_pool: PgPool,
+}
+
+impl PostgresAdminPasskeyRepo {
+    /// Reads use the primary as well, so a stale replica can't hand back a
+    /// signature counter older than the last successful sign-in.
+    pub fn new(write_pool: PgPool) -> Self {
+        Self { write_pool }
+    }
+
+    fn parse_passkey(row: &PgRow) -> AdminPasskey {
+        AdminPasskey {
+            id: row.get("id"),
+            user_id: row.get("user_id"),
+            credential_id: row.get("credential_id"),
+            name: row.get("name"),
+            credential: row.get("credential"),
+            last_used_at: row.get("last_used_at"),
+            created_at: row.get("created_at"),
+        }
+    }
+}
+
+#[async_trait]
+impl AdminPasskeyRepo for PostgresAdminPasskeyRepo {
+    async fn create(&self, input: CreateAdminPasskey) -> DbResult<AdminPasskey> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+
+        sqlx::query(
+            r#"
+            INSERT INTO admin_passkeys (
+                id, user_id, credential_id, name, credential, last_used_at, created_at
+            )
+            VALUES ($1, $2, $3, $4, $5, NULL, $6)
+            "#,
+        )
+        .bind(id)
+        .bind(input.user_id)
+        .bind(&input.credential_id)
+        .bind(&input.name)
+        .bind(&input.credential)
+        .bind(now)
+        .execute(&self.write_pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+                DbError::Conflict("Passkey is already registered".to_string())
+            }
+            _ => DbError::from(e),
+        })?;
+
+        Ok(AdminPasskey {
+            id,
+            user_id: input.user_id,
+            credential_id: input.credential_id,
+            name: input.name,
+            credential: input.credential,
+            last_used_at: None,
+            created_at: now,
+        })
+    }
+
+    async fn list_by_user(&self, user_id: Uuid) -> DbResult<Vec<AdminPasskey>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, user_id, credential_id, name, credential, last_used_at, created_at
+            FROM admin_passkeys
+            WHERE user_id = $1
+            ORDER BY created_at ASC, id ASC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.write_pool)
+        .await?;
+
+        Ok(rows.iter().map(Self::parse_passkey).collect())
+    }
+
+    async fn get_by_credential_id(&self, credential_id: &str) -> DbResult<Option<AdminPasskey>> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, user_id, credential_id, name, credential, last_used_at, created_at
+            FROM admin_passkeys
+            WHERE credential_id = $1
+            "#,
+        )
+        .bind(credential_id)
+        .fetch_optional(&self.write_pool)
+        .await?;
+
+        Ok(row.as_ref().map(Self::parse_passkey))
+    }
+
+    async fn record_use(&self, id: Uuid, credential: &str) -> DbResult<()> {
+        let now = truncate_to_millis(Utc::now());
+        sqlx::query("UPDATE admin_passkeys SET credential = $1, last_used_at = $2 WHERE id = $3")
+            .bind(credential)
+            .bind(now)
+            .bind(id)
+            .execute(&self.write_pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, user_id: Uuid, id: Uuid) -> DbResult<bool> {
+        let result = sqlx::query("DELETE FROM admin_passkeys WHERE id = $1 AND user_id = $2")
+            .bind(id)
+            .bind(user_id)
+            .execute(&self.write_pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,133 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::{PgPool, Row, postgres::PgRow};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        error::DbResult,
+        repos::{AdminTotpRepo, truncate_to_millis},
+    },
+    models::AdminTotpEnrollment,
+};
+
+pub struct PostgresAdminTotpRepo {
+    write_pool: PgPool,
+}
+
+impl PostgresAdminTotpRepo {
+    /// Reads go to the primary too: a code accepted a moment ago must not
+    /// look unused because a replica hasn't caught up.
+    pub fn new(write_pool: PgPool) -> Self {
+        Self { write_pool }
+    }
+
+    fn parse_enrollment(row: &PgRow) -> AdminTotpEnrollment {
+        AdminTotpEnrollment {
+            user_id: row.get("user_id"),
+            secret: row.get("secret"),
+            verified_at: row.get("verified_at"),
+            last_used_step: row.get("last_used_step"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        }
+    }
+}
+
+#[async_trait]
+impl AdminTotpRepo for PostgresAdminTotpRepo {
+    async fn get(&self, user_id: Uuid) -> DbResult<Option<AdminTotpEnrollment>> {
+        let row = sqlx::query(
+            r#"
+            SELECT user_id, secret, verified_at, last_used_step, created_at, updated_at
+            FROM admin_totp_enrollments
+            WHERE user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(&self.write_pool)
+        .await?;
+
+        Ok(row.as_ref().map(Self::parse_enrollment))
+    }
+
+    async fn upsert_pending(&self, user_id: Uuid, secret: &str) -> DbResult<AdminTotpEnrollment> {
+        let now = truncate_to_millis(Utc::now());
+
+        sqlx::query(
+            r#"
+            INSERT INTO admin_totp_enrollments (
+                user_id, secret, verified_at, last_used_step, created_at, updated_at
+            )
+            VALUES ($1, $2, NULL, NULL, $3, $3)
+            ON CONFLICT (user_id) DO UPDATE SET
+                secret = EXCLUDED.secret,
+                verified_at = NULL,
+                last_used_step = NULL,
+                created_at = EXCLUDED.created_at,
+                updated_at = EXCLUDED.updated_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(secret)
+        .bind(now)
+        .execute(&self.write_pool)
+        .await?;
+
+        Ok(AdminTotpEnrollment {
+            user_id,
+            secret: secret.to_string(),
+            verified_at: None,
+            last_used_step: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn mark_verified(&self, user_id: Uuid, step: i64) -> DbResult<bool> {
+        let now = truncate_to_millis(Utc::now());
+        let result = sqlx::query(
+            r#"
+            UPDATE admin_totp_enrollments
+            SET verified_at = $1, last_used_step = $2, updated_at = $1
+            WHERE user_id = $3 AND verified_at IS NULL
+            "#,
+        )
+        .bind(now)
+        .bind(step)
+        .bind(user_id)
+        .execute(&self.write_pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn record_use(&self, user_id: Uuid, step: i64) -> DbResult<bool> {
+        let now = truncate_to_millis(Utc::now());
+        let result = sqlx::query(
+            r#"
+            UPDATE admin_totp_enrollments
+            SET last_used_step = $1, updated_at = $2
+            WHERE user_id = $3
+              AND verified_at IS NOT NULL
+              AND (last_used_step IS NULL OR last_used_step < $1)
+            "#,
+        )
+        .bind(step)
+        .bind(now)
+        .bind(user_id)
+        .execute(&self.write_pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn delete(&self, user_id: Uuid) -> DbResult<bool> {
+        let result = sqlx::query("DELETE FROM admin_totp_enrollments WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&self.write_pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,375 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::{PgPool, Row, postgres::PgRow};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{AgentRepo, truncate_to_millis},
+    },
+    models::{
+        Agent, AgentRun, AgentRunOutcome, AgentRunStatus, AgentRunStep, CreateAgent,
+        CreateAgentRun, UpdateAgent,
+    },
+};
+
+const AGENT_COLUMNS: &str = "id, org_id, name, description, model, instructions, tools, \
+                             vector_store_ids, max_iterations, created_at, updated_at";
+
+const RUN_COLUMNS: &str = "id, agent_id, org_id, project_id, user_id, api_key_id, \
+                           service_account_id, provider, model, status, input, output_text, \
+                           error, input_tokens, output_tokens, cost_microcents, created_at, \
+                           completed_at";
+
+const STEP_COLUMNS: &str = "id, run_id, sequence, step_type, content, created_at";
+
+pub struct PostgresAgentRepo {
+    write_pool: PgPool,
+    read_pool: PgPool,
+}
+
+impl PostgresAgentRepo {
+    pub fn new(write_pool: PgPool, read_pool: Option<PgPool>) -> Self {
+        let read_pool = read_pool.unwrap_or_else(|| write_pool.clone());
+        Self {
+            write_pool,
+            read_pool,
+        }
+    }
+
+    fn parse_agent(row: &PgRow) -> DbResult<Agent> {
+        Ok(Agent {
+            id: row.get("id"),
+            org_id: row.get("org_id"),
+            name: row.get("name"),
+            description: row.get("description"),
+            model: row.get("model"),
+            instructions: row.get("instructions"),
+            tools: serde_json::from_value(row.get("tools"))?,
+            vector_store_ids: serde_json::from_value(row.get("vector_store_ids"))?,
+            max_iterations: row.get("max_iterations"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        })
+    }
+
+    fn parse_run(row: &PgRow) -> DbResult<AgentRun> {
+        let status: String = row.get("status");
+        Ok(AgentRun {
+            id: row.get("id"),
+            agent_id: row.get("agent_id"),
+            org_id: row.get("org_id"),
+            project_id: row.get("project_id"),
+            user_id: row.get("user_id"),
+            api_key_id: row.get("api_key_id"),
+            service_account_id: row.get("service_account_id"),
+            provider: row.get("provider"),
+            model: row.get("model"),
+            status: status.parse().map_err(DbError::Internal)?,
+            input: row.get("input"),
+            output_text: row.get("output_text"),
+            error: row.get("error"),
+            input_tokens: row.get("input_tokens"),
+            output_tokens: row.get("output_tokens"),
+            cost_microcents: row.get("cost_microcents"),
+            created_at: row.get("created_at"),
+            completed_at: row.get("completed_at"),
+        })
+    }
+
+    fn parse_step(row: &PgRow) -> AgentRunStep {
+        AgentRunStep {
+            id: row.get("id"),
+            run_id: row.get("run_id"),
+            sequence: row.get("sequence"),
+            step_type: row.get("step_type"),
+            content: row.get("content"),
+            created_at: row.get("created_at"),
+        }
+    }
+
+    fn map_name_conflict(name: &str) -> impl FnOnce(sqlx::Error) -> DbError + '_ {
+        move |e| match e {
+            sqlx::Error::Database(db_err) if db_err.is_unique_violation() => DbError::Conflict(
+                format!("Agent '{name}' already exists in this organization"),
+            ),
+            _ => DbError::from(e),
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl AgentRepo for PostgresAgentRepo {
+    async fn create_agent(&self, org_id: Uuid, input: CreateAgent) -> DbResult<Agent> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+
+        sqlx::query(
+            r#"
+            INSERT INTO agents (
+                id, org_id, name, description, model, instructions, tools, vector_store_ids,
+                max_iterations, created_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            "#,
+        )
+        .bind(id)
+        .bind(org_id)
+        .bind(&input.name)
+        .bind(&input.description)
+        .bind(&input.model)
+        .bind(&input.instructions)
+        .bind(serde_json::to_value(&input.tools)?)
+        .bind(serde_json::to_value(&input.vector_store_ids)?)
+        .bind(input.max_iterations)
+        .bind(now)
+        .bind(now)
+        .execute(&self.write_pool)
+        .await
+        .map_err(Self::map_name_conflict(&input.name))?;
+
+        Ok(Agent {
+            id,
+            org_id,
+            name: input.name,
+            description: input.description,
+            model: input.model,
+            instructions: input.instructions,
+            tools: input.tools,
+            vector_store_ids: input.vector_store_ids,
+            max_iterations: input.max_iterations,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn get_agent(&self, id: Uuid) -> DbResult<Option<Agent>> {
+        let sql = format!("SELECT {AGENT_COLUMNS} FROM agents WHERE id = $1");
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .fetch_optional(&self.read_pool)
+            .await?;
+
+        row.as_ref().map(Self::parse_agent).transpose()
+    }
+
+    async fn list_agents(&self, org_id: Uuid) -> DbResult<Vec<Agent>> {
+        let sql = format!("SELECT {AGENT_COLUMNS} FROM agents WHERE org_id = $1 ORDER BY name ASC");
+        let rows = sqlx::query(&sql)
+            .bind(org_id)
+            .fetch_all(&self.read_pool)
+            .await?;
+
+        rows.iter().map(Self::parse_agent).collect()
+    }
+
+    async fn update_agent(&self, id: Uuid, input: UpdateAgent) -> DbResult<Agent> {
+        let tools = input.tools.as_ref().map(serde_json::to_value).transpose()?;
+        let vector_store_ids = input
+            .vector_store_ids
+            .as_ref()
+            .map(serde_json::to_value)
+            .transpose()?;
+        let name = input.name.clone().unwrap_or_default();
+        let sql = format!(
+            "UPDATE agents SET \
+                name = COALESCE($1, name), \
+                description = COALESCE($2, description), \
+                model = COALESCE($3, model), \
+                instructions = COALESCE($4, instructions), \
+                tools = COALESCE($5, tools), \
+                vector_store_ids = COALESCE($6, vector_store_ids), \
+                max_iterations = COALESCE($7, max_iterations), \
+                updated_at = $8 \
+             WHERE id = $9 \
+             RETURNING {AGENT_COLUMNS}"
+        );
+        let row = sqlx::query(&sql)
+            .bind(&input.name)
+            .bind(&input.description)
+            .bind(&input.model)
+            .bind(&input.instructions)
+            .bind(tools)
+            .bind(vector_store_ids)
+            .bind(input.max_iterations)
+            .bind(truncate_to_millis(Utc::now()))
+            .bind(id)
+            .fetch_optional(&self.write_pool)
+            .await
+            .map_err(Self::map_name_conflict(&name))?
+            .ok_or(DbError::NotFound)?;
+
+        Self::parse_agent(&row)
+    }
+
+    async fn delete_agent(&self, id: Uuid) -> DbResult<()> {
+        // Runs and their steps go with the agent via ON DELETE CASCADE
+        let result = sqlx::query("DELETE FROM agents WHERE id = $1")
+            .bind(id)
+            .execute(&self.write_pool)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+        Ok(())
+    }
+
+    async fn create_run(&self, input: CreateAgentRun) -> DbResult<AgentRun> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+        let status = AgentRunStatus::InProgress;
+
+        sqlx::query(
+            r#"
+            INSERT INTO agent_runs (
+                id, agent_id, org_id, project_id, user_id, api_key_id, service_account_id,
+                provider, model, status, input, input_tokens, output_tokens, created_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, 0, 0, $12)
+            "#,
+        )
+        .bind(id)
+        .bind(input.agent_id)
+        .bind(input.org_id)
+        .bind(input.project_id)
+        .bind(input.user_id)
+        .bind(input.api_key_id)
+        .bind(input.service_account_id)
+        .bind(&input.provider)
+        .bind(&input.model)
+        .bind(status.as_str())
+        .bind(&input.input)
+        .bind(now)
+        .execute(&self.write_pool)
+        .await?;
+
+        Ok(AgentRun {
+            id,
+            agent_id: input.agent_id,
+            org_id: input.org_id,
+            project_id: input.project_id,
+            user_id: input.user_id,
+            api_key_id: input.api_key_id,
+            service_account_id: input.service_account_id,
+            provider: input.provider,
+            model: input.model,
+            status,
+            input: input.input,
+            output_text: None,
+            error: None,
+            input_tokens: 0,
+            output_tokens: 0,
+            cost_microcents: None,
+            created_at: now,
+            completed_at: None,
+        })
+    }
+
+    async fn get_run(&self, id: Uuid) -> DbResult<Option<AgentRun>> {
+        let sql = format!("SELECT {RUN_COLUMNS} FROM agent_runs WHERE id = $1");
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .fetch_optional(&self.read_pool)
+            .await?;
+
+        row.as_ref().map(Self::parse_run).transpose()
+    }
+
+    async fn list_runs(
+        &self,
+        agent_id: Uuid,
+        project_id: Option<Uuid>,
+        limit: i64,
+    ) -> DbResult<Vec<AgentRun>> {
+        let sql = format!(
+            "SELECT {RUN_COLUMNS} FROM agent_runs \
+             WHERE agent_id = $1 AND ($2::UUID IS NULL OR project_id = $2) \
+             ORDER BY created_at DESC, id DESC LIMIT $3"
+        );
+        let rows = sqlx::query(&sql)
+            .bind(agent_id)
+            .bind(project_id)
+            .bind(limit)
+            .fetch_all(&self.read_pool)
+            .await?;
+
+        rows.iter().map(Self::parse_run).collect()
+    }
+
+    async fn add_step(
+        &self,
+        run_id: Uuid,
+        sequence: i32,
+        step_type: &str,
+        content: &serde_json::Value,
+    ) -> DbResult<AgentRunStep> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+
+        sqlx::query(
+            r#"
+            INSERT INTO agent_run_steps (id, run_id, sequence, step_type, content, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(id)
+        .bind(run_id)
+        .bind(sequence)
+        .bind(step_type)
+        .bind(content)
+        .bind(now)
+        .execute(&self.write_pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+                DbError::Conflict(format!("Run '{run_id}' already has step {sequence}"))
+            }
+            _ => DbError::from(e),
+        })?;
+
+        Ok(AgentRunStep {
+            id,
+            run_id,
+            sequence,
+            step_type: step_type.to_string(),
+            content: content.clone(),
+            created_at: now,
+        })
+    }
+
+    async fn list_steps(&self, run_id: Uuid) -> DbResult<Vec<AgentRunStep>> {
+        let sql = format!(
+            "SELECT {STEP_COLUMNS} FROM agent_run_steps WHERE run_id = $1 ORDER BY sequence ASC"
+        );
+        let rows = sqlx::query(&sql)
+            .bind(run_id)
+            .fetch_all(&self.read_pool)
+            .await?;
+
+        Ok(rows.iter().map(Self::parse_step).collect())
+    }
+
+    async fn finish_run(&self, id: Uuid, outcome: &AgentRunOutcome) -> DbResult<AgentRun> {
+        let sql = format!(
+            "UPDATE agent_runs SET status = $1, output_text = $2, error = $3, input_tokens = $4, \
+             output_tokens = $5, cost_microcents = $6, completed_at = $7 WHERE id = $8 \
+             RETURNING {RUN_COLUMNS}"
+        );
+        let row = sqlx::query(&sql)
+            .bind(outcome.status.as_str())
+            .bind(&outcome.output_text)
+            .bind(&outcome.error)
+            .bind(outcome.input_tokens)
+            .bind(outcome.output_tokens)
+            .bind(outcome.cost_microcents)
+            .bind(truncate_to_millis(Utc::now()))
+            .bind(id)
+            .fetch_optional(&self.write_pool)
+            .await?
+            .ok_or(DbError::NotFound)?;
+
+        Self::parse_run(&row)
+    }
+}
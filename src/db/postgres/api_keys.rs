@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use sqlx::{PgPool, Row};
@@ -11,7 +13,7 @@ use crate::{
             cursor_from_row,
         },
     },
-    models::{ApiKey, ApiKeyOwner, ApiKeyWithOwner, BudgetPeriod, CreateApiKey},
+    models::{ApiKey, ApiKeyHash, ApiKeyOwner, ApiKeyWithOwner, BudgetPeriod, CreateApiKey},
 };
 
 pub struct PostgresApiKeyRepo {
@@ -108,6 +110,8 @@ impl PostgresApiKeyRepo {
                         "failed to deserialize sovereignty_requirements: {e}"
                     ))
                 })?,
+            labels: serde_json::from_value(row.get("labels"))
+                .map_err(|e| DbError::Internal(format!("failed to deserialize labels: {e}")))?,
         })
     }
 
@@ -128,12 +132,13 @@ impl PostgresApiKeyRepo {
             SELECT id, key_prefix, name, owner_type::TEXT, owner_id, budget_amount, budget_period::TEXT,
                    expires_at, last_used_at, created_at, revoked_at,
                    scopes, allowed_models, ip_allowlist, rate_limit_rpm, rate_limit_tpm,
-                   rotated_from_key_id, rotation_grace_until, sovereignty_requirements
+                   rotated_from_key_id, rotation_grace_until, sovereignty_requirements, labels
             FROM api_keys
             WHERE owner_type = 'organization' AND owner_id = $1
             AND ROW(created_at, id) {} ROW($2, $3)
+            AND labels @> $4
             ORDER BY created_at {}, id {}
-            LIMIT $4
+            LIMIT $5
             "#,
             comparison, order, order
         );
@@ -142,6 +147,7 @@ impl PostgresApiKeyRepo {
             .bind(org_id)
             .bind(cursor.created_at)
             .bind(cursor.id)
+            .bind(params.labels.to_json())
             .bind(fetch_limit)
             .fetch_all(&self.read_pool)
             .await?;
@@ -182,12 +188,13 @@ impl PostgresApiKeyRepo {
             SELECT id, key_prefix, name, owner_type::TEXT, owner_id, budget_amount, budget_period::TEXT,
                    expires_at, last_used_at, created_at, revoked_at,
                    scopes, allowed_models, ip_allowlist, rate_limit_rpm, rate_limit_tpm,
-                   rotated_from_key_id, rotation_grace_until, sovereignty_requirements
+                   rotated_from_key_id, rotation_grace_until, sovereignty_requirements, labels
             FROM api_keys
             WHERE owner_type = 'project' AND owner_id = $1
             AND ROW(created_at, id) {} ROW($2, $3)
+            AND labels @> $4
             ORDER BY created_at {}, id {}
-            LIMIT $4
+            LIMIT $5
             "#,
             comparison, order, order
         );
@@ -196,6 +203,7 @@ impl PostgresApiKeyRepo {
             .bind(project_id)
             .bind(cursor.created_at)
             .bind(cursor.id)
+            .bind(params.labels.to_json())
             .bind(fetch_limit)
             .fetch_all(&self.read_pool)
             .await?;
@@ -236,12 +244,13 @@ impl PostgresApiKeyRepo {
             SELECT id, key_prefix, name, owner_type::TEXT, owner_id, budget_amount, budget_period::TEXT,
                    expires_at, last_used_at, created_at, revoked_at,
                    scopes, allowed_models, ip_allowlist, rate_limit_rpm, rate_limit_tpm,
-                   rotated_from_key_id, rotation_grace_until, sovereignty_requirements
+                   rotated_from_key_id, rotation_grace_until, sovereignty_requirements, labels
             FROM api_keys
             WHERE owner_type = 'team' AND owner_id = $1
             AND ROW(created_at, id) {} ROW($2, $3)
+            AND labels @> $4
             ORDER BY created_at {}, id {}
-            LIMIT $4
+            LIMIT $5
             "#,
             comparison, order, order
         );
@@ -250,6 +259,7 @@ impl PostgresApiKeyRepo {
             .bind(team_id)
             .bind(cursor.created_at)
             .bind(cursor.id)
+            .bind(params.labels.to_json())
             .bind(fetch_limit)
             .fetch_all(&self.read_pool)
             .await?;
@@ -290,12 +300,13 @@ impl PostgresApiKeyRepo {
             SELECT id, key_prefix, name, owner_type::TEXT, owner_id, budget_amount, budget_period::TEXT,
                    expires_at, last_used_at, created_at, revoked_at,
                    scopes, allowed_models, ip_allowlist, rate_limit_rpm, rate_limit_tpm,
-                   rotated_from_key_id, rotation_grace_until, sovereignty_requirements
+                   rotated_from_key_id, rotation_grace_until, sovereignty_requirements, labels
             FROM api_keys
             WHERE owner_type = 'user' AND owner_id = $1
             AND ROW(created_at, id) {} ROW($2, $3)
+            AND labels @> $4
             ORDER BY created_at {}, id {}
-            LIMIT $4
+            LIMIT $5
             "#,
             comparison, order, order
         );
@@ -304,6 +315,7 @@ impl PostgresApiKeyRepo {
             .bind(user_id)
             .bind(cursor.created_at)
             .bind(cursor.id)
+            .bind(params.labels.to_json())
             .bind(fetch_limit)
             .fetch_all(&self.read_pool)
             .await?;
@@ -344,12 +356,13 @@ impl PostgresApiKeyRepo {
             SELECT id, key_prefix, name, owner_type::TEXT, owner_id, budget_amount, budget_period::TEXT,
                    expires_at, last_used_at, created_at, revoked_at,
                    scopes, allowed_models, ip_allowlist, rate_limit_rpm, rate_limit_tpm,
-                   rotated_from_key_id, rotation_grace_until, sovereignty_requirements
+                   rotated_from_key_id, rotation_grace_until, sovereignty_requirements, labels
             FROM api_keys
             WHERE owner_type = 'service_account' AND owner_id = $1
             AND ROW(created_at, id) {} ROW($2, $3)
+            AND labels @> $4
             ORDER BY created_at {}, id {}
-            LIMIT $4
+            LIMIT $5
             "#,
             comparison, order, order
         );
@@ -358,6 +371,7 @@ impl PostgresApiKeyRepo {
             .bind(service_account_id)
             .bind(cursor.created_at)
             .bind(cursor.id)
+            .bind(params.labels.to_json())
             .bind(fetch_limit)
             .fetch_all(&self.read_pool)
             .await?;
@@ -385,8 +399,9 @@ impl PostgresApiKeyRepo {
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 impl ApiKeyRepo for PostgresApiKeyRepo {
-    async fn create(&self, input: CreateApiKey, key_hash: &str) -> DbResult<ApiKey> {
+    async fn create_with_hash(&self, input: CreateApiKey, hash: &ApiKeyHash) -> DbResult<ApiKey> {
         let id = Uuid::new_v4();
+        let key_hash = hash.key_hash.as_str();
 
         // Extract first 8 characters of hash as prefix
         let key_prefix = if key_hash.len() >= 8 {
@@ -403,9 +418,9 @@ impl ApiKeyRepo for PostgresApiKeyRepo {
                 id, name, key_hash, key_prefix, owner_type, owner_id,
                 budget_amount, budget_period, expires_at,
                 scopes, allowed_models, ip_allowlist, rate_limit_rpm, rate_limit_tpm,
-                sovereignty_requirements
+                sovereignty_requirements, hash_scheme, key_verifier
             )
-            VALUES ($1, $2, $3, $4, $5::api_key_owner_type, $6, $7, $8::budget_period, $9, $10, $11, $12, $13, $14, $15)
+            VALUES ($1, $2, $3, $4, $5::api_key_owner_type, $6, $7, $8::budget_period, $9, $10, $11, $12, $13, $14, $15, $16, $17)
             RETURNING created_at
             "#,
         )
@@ -444,6 +459,8 @@ impl ApiKeyRepo for PostgresApiKeyRepo {
                 .as_ref()
                 .and_then(|s| serde_json::to_value(s).ok()),
         )
+        .bind(hash.scheme.as_str())
+        .bind(&hash.verifier)
         .fetch_one(&self.write_pool)
         .await
         .map_err(|e| match e {
@@ -472,6 +489,7 @@ impl ApiKeyRepo for PostgresApiKeyRepo {
             rotated_from_key_id: None,
             rotation_grace_until: None,
             sovereignty_requirements: input.sovereignty_requirements,
+            labels: Default::default(),
         })
     }
 
@@ -482,7 +500,7 @@ impl ApiKeyRepo for PostgresApiKeyRepo {
                 id, key_prefix, name, owner_type::TEXT, owner_id,
                 budget_amount, budget_period::TEXT, expires_at, last_used_at, created_at, revoked_at,
                 scopes, allowed_models, ip_allowlist, rate_limit_rpm, rate_limit_tpm,
-                rotated_from_key_id, rotation_grace_until, sovereignty_requirements
+                rotated_from_key_id, rotation_grace_until, sovereignty_requirements, labels
             FROM api_keys
             WHERE id = $1
             "#,
@@ -506,7 +524,8 @@ impl ApiKeyRepo for PostgresApiKeyRepo {
                 k.budget_amount, k.budget_period::TEXT, k.expires_at, k.last_used_at, k.created_at,
                 k.revoked_at,
                 k.scopes, k.allowed_models, k.ip_allowlist, k.rate_limit_rpm, k.rate_limit_tpm,
-                k.rotated_from_key_id, k.rotation_grace_until, k.sovereignty_requirements,
+                k.rotated_from_key_id, k.rotation_grace_until, k.sovereignty_requirements, k.labels,
+                k.hash_scheme, k.key_verifier,
                 CASE
                     WHEN k.owner_type = 'organization' THEN k.owner_id
                     WHEN k.owner_type = 'team' THEN t.org_id
@@ -550,6 +569,8 @@ impl ApiKeyRepo for PostgresApiKeyRepo {
             user_id: row.get("user_id"),
             service_account_id: row.get("service_account_id"),
             service_account_roles,
+            hash_scheme: row.get("hash_scheme"),
+            key_verifier: row.get("key_verifier"),
         }))
     }
 
@@ -571,14 +592,16 @@ impl ApiKeyRepo for PostgresApiKeyRepo {
                 id, key_prefix, name, owner_type::TEXT, owner_id,
                 budget_amount, budget_period::TEXT, expires_at, last_used_at, created_at, revoked_at,
                 scopes, allowed_models, ip_allowlist, rate_limit_rpm, rate_limit_tpm,
-                rotated_from_key_id, rotation_grace_until, sovereignty_requirements
+                rotated_from_key_id, rotation_grace_until, sovereignty_requirements, labels
             FROM api_keys
             WHERE owner_type = 'organization' AND owner_id = $1
+            AND labels @> $2
             ORDER BY created_at DESC, id DESC
-            LIMIT $2
+            LIMIT $3
             "#,
         )
         .bind(org_id)
+        .bind(params.labels.to_json())
         .bind(fetch_limit)
         .fetch_all(&self.read_pool)
         .await?;
@@ -630,14 +653,16 @@ impl ApiKeyRepo for PostgresApiKeyRepo {
                 id, key_prefix, name, owner_type::TEXT, owner_id,
                 budget_amount, budget_period::TEXT, expires_at, last_used_at, created_at, revoked_at,
                 scopes, allowed_models, ip_allowlist, rate_limit_rpm, rate_limit_tpm,
-                rotated_from_key_id, rotation_grace_until, sovereignty_requirements
+                rotated_from_key_id, rotation_grace_until, sovereignty_requirements, labels
             FROM api_keys
             WHERE owner_type = 'team' AND owner_id = $1
+            AND labels @> $2
             ORDER BY created_at DESC, id DESC
-            LIMIT $2
+            LIMIT $3
             "#,
         )
         .bind(team_id)
+        .bind(params.labels.to_json())
         .bind(fetch_limit)
         .fetch_all(&self.read_pool)
         .await?;
@@ -689,14 +714,16 @@ impl ApiKeyRepo for PostgresApiKeyRepo {
                 id, key_prefix, name, owner_type::TEXT, owner_id,
                 budget_amount, budget_period::TEXT, expires_at, last_used_at, created_at, revoked_at,
                 scopes, allowed_models, ip_allowlist, rate_limit_rpm, rate_limit_tpm,
-                rotated_from_key_id, rotation_grace_until, sovereignty_requirements
+                rotated_from_key_id, rotation_grace_until, sovereignty_requirements, labels
             FROM api_keys
             WHERE owner_type = 'project' AND owner_id = $1
+            AND labels @> $2
             ORDER BY created_at DESC, id DESC
-            LIMIT $2
+            LIMIT $3
             "#,
         )
         .bind(project_id)
+        .bind(params.labels.to_json())
         .bind(fetch_limit)
         .fetch_all(&self.read_pool)
         .await?;
@@ -748,14 +775,16 @@ impl ApiKeyRepo for PostgresApiKeyRepo {
                 id, key_prefix, name, owner_type::TEXT, owner_id,
                 budget_amount, budget_period::TEXT, expires_at, last_used_at, created_at, revoked_at,
                 scopes, allowed_models, ip_allowlist, rate_limit_rpm, rate_limit_tpm,
-                rotated_from_key_id, rotation_grace_until, sovereignty_requirements
+                rotated_from_key_id, rotation_grace_until, sovereignty_requirements, labels
             FROM api_keys
             WHERE owner_type = 'user' AND owner_id = $1
+            AND labels @> $2
             ORDER BY created_at DESC, id DESC
-            LIMIT $2
+            LIMIT $3
             "#,
         )
         .bind(user_id)
+        .bind(params.labels.to_json())
         .bind(fetch_limit)
         .fetch_all(&self.read_pool)
         .await?;
@@ -799,6 +828,28 @@ impl ApiKeyRepo for PostgresApiKeyRepo {
         Ok(row.get::<i64, _>("count"))
     }
 
+    async fn count_active_by_org(&self, org_id: Uuid) -> DbResult<i64> {
+        let row = sqlx::query(
+            r#"
+            SELECT COUNT(*) as count
+            FROM api_keys
+            WHERE revoked_at IS NULL
+              AND (expires_at IS NULL OR expires_at >= NOW())
+              AND (
+                (owner_type = 'organization' AND owner_id = $1)
+                OR (owner_type = 'team' AND owner_id IN (SELECT id FROM teams WHERE org_id = $1))
+                OR (owner_type = 'project' AND owner_id IN (SELECT id FROM projects WHERE org_id = $1))
+                OR (owner_type = 'service_account'
+                    AND owner_id IN (SELECT id FROM service_accounts WHERE org_id = $1))
+              )
+            "#,
+        )
+        .bind(org_id)
+        .fetch_one(&self.read_pool)
+        .await?;
+        Ok(row.get::<i64, _>("count"))
+    }
+
     async fn revoke(&self, id: Uuid) -> DbResult<()> {
         sqlx::query(
             r#"
@@ -872,14 +923,16 @@ impl ApiKeyRepo for PostgresApiKeyRepo {
                 id, key_prefix, name, owner_type::TEXT, owner_id,
                 budget_amount, budget_period::TEXT, expires_at, last_used_at, created_at, revoked_at,
                 scopes, allowed_models, ip_allowlist, rate_limit_rpm, rate_limit_tpm,
-                rotated_from_key_id, rotation_grace_until, sovereignty_requirements
+                rotated_from_key_id, rotation_grace_until, sovereignty_requirements, labels
             FROM api_keys
             WHERE owner_type = 'service_account' AND owner_id = $1
+            AND labels @> $2
             ORDER BY created_at DESC, id DESC
-            LIMIT $2
+            LIMIT $3
             "#,
         )
         .bind(service_account_id)
+        .bind(params.labels.to_json())
         .bind(fetch_limit)
         .fetch_all(&self.read_pool)
         .await?;
@@ -931,14 +984,15 @@ impl ApiKeyRepo for PostgresApiKeyRepo {
         Ok(result.rows_affected())
     }
 
-    async fn rotate(
+    async fn rotate_with_hash(
         &self,
         old_key_id: Uuid,
         new_key_input: CreateApiKey,
-        new_key_hash: &str,
+        hash: &ApiKeyHash,
         grace_until: DateTime<Utc>,
     ) -> DbResult<ApiKey> {
         let new_id = Uuid::new_v4();
+        let new_key_hash = hash.key_hash.as_str();
 
         // Extract first 8 characters of hash as prefix
         let key_prefix = if new_key_hash.len() >= 8 {
@@ -972,10 +1026,11 @@ impl ApiKeyRepo for PostgresApiKeyRepo {
                 id, name, key_hash, key_prefix, owner_type, owner_id,
                 budget_amount, budget_period, expires_at,
                 scopes, allowed_models, ip_allowlist, rate_limit_rpm, rate_limit_tpm,
-                sovereignty_requirements, rotated_from_key_id
+                sovereignty_requirements, rotated_from_key_id, hash_scheme, key_verifier, labels
             )
-            VALUES ($1, $2, $3, $4, $5::api_key_owner_type, $6, $7, $8::budget_period, $9, $10, $11, $12, $13, $14, $15, $16)
-            RETURNING created_at
+            VALUES ($1, $2, $3, $4, $5::api_key_owner_type, $6, $7, $8::budget_period, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18,
+                    COALESCE((SELECT labels FROM api_keys WHERE id = $16), '{}'))
+            RETURNING created_at, labels
             "#,
         )
         .bind(new_id)
@@ -1014,6 +1069,8 @@ impl ApiKeyRepo for PostgresApiKeyRepo {
                 .and_then(|s| serde_json::to_value(s).ok()),
         )
         .bind(old_key_id)
+        .bind(hash.scheme.as_str())
+        .bind(&hash.verifier)
         .fetch_one(&mut *tx)
         .await
         .map_err(|e| match e {
@@ -1044,9 +1101,35 @@ impl ApiKeyRepo for PostgresApiKeyRepo {
             rotated_from_key_id: Some(old_key_id),
             rotation_grace_until: None,
             sovereignty_requirements: new_key_input.sovereignty_requirements,
+            labels: serde_json::from_value(row.get("labels"))
+                .map_err(|e| DbError::Internal(format!("failed to deserialize labels: {e}")))?,
         })
     }
 
+    async fn set_labels(&self, id: Uuid, labels: &BTreeMap<String, String>) -> DbResult<ApiKey> {
+        let labels = serde_json::to_value(labels)
+            .map_err(|e| DbError::Internal(format!("failed to serialize labels: {e}")))?;
+
+        let row = sqlx::query(
+            r#"
+            UPDATE api_keys
+            SET labels = $1, updated_at = NOW()
+            WHERE id = $2
+            RETURNING id, key_prefix, name, owner_type::TEXT, owner_id,
+                budget_amount, budget_period::TEXT, expires_at, last_used_at, created_at, revoked_at,
+                scopes, allowed_models, ip_allowlist, rate_limit_rpm, rate_limit_tpm,
+                rotated_from_key_id, rotation_grace_until, sovereignty_requirements, labels
+            "#,
+        )
+        .bind(labels)
+        .bind(id)
+        .fetch_optional(&self.write_pool)
+        .await?
+        .ok_or(DbError::NotFound)?;
+
+        Self::parse_api_key(&row)
+    }
+
     async fn get_key_hashes_by_service_account(
         &self,
         service_account_id: Uuid,
@@ -1084,6 +1167,31 @@ impl ApiKeyRepo for PostgresApiKeyRepo {
         Ok(hashes)
     }
 
+    async fn update_hash(&self, id: Uuid, current_hash: &str, hash: &ApiKeyHash) -> DbResult<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE api_keys
+            SET key_hash = $1, hash_scheme = $2, key_verifier = $3
+            WHERE id = $4 AND key_hash = $5
+            "#,
+        )
+        .bind(&hash.key_hash)
+        .bind(hash.scheme.as_str())
+        .bind(&hash.verifier)
+        .bind(id)
+        .bind(current_hash)
+        .execute(&self.write_pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+                DbError::Conflict("API key with this hash already exists".to_string())
+            }
+            _ => DbError::from(e),
+        })?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     async fn get_by_name_and_org(&self, org_id: Uuid, name: &str) -> DbResult<Option<ApiKey>> {
         let row = sqlx::query(
             r#"
@@ -1091,7 +1199,7 @@ impl ApiKeyRepo for PostgresApiKeyRepo {
                 id, key_prefix, name, owner_type::TEXT, owner_id,
                 budget_amount, budget_period::TEXT, expires_at, last_used_at, created_at, revoked_at,
                 scopes, allowed_models, ip_allowlist, rate_limit_rpm, rate_limit_tpm,
-                rotated_from_key_id, rotation_grace_until, sovereignty_requirements
+                rotated_from_key_id, rotation_grace_until, sovereignty_requirements, labels
             FROM api_keys
             WHERE name = $1 AND owner_type = 'organization' AND owner_id = $2 AND revoked_at IS NULL
             "#,
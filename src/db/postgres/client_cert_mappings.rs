@@ -0,0 +1,219 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::{PgPool, Row, postgres::PgRow};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{
+            ClientCertMappingRepo, CursorDirection, ListParams, ListResult, PageCursors,
+            cursor_from_row, truncate_to_millis,
+        },
+    },
+    models::{CertMatchType, ClientCertMapping, CreateClientCertMapping},
+};
+
+const COLUMNS: &str = "m.id, m.org_id, m.service_account_id, m.match_type::TEXT AS match_type, \
+                       m.match_value, m.description, m.created_at";
+
+pub struct PostgresClientCertMappingRepo {
+    write_pool: PgPool,
+    read_pool: PgPool,
+}
+
+impl PostgresClientCertMappingRepo {
+    pub fn new(write_pool: PgPool, read_pool: Option<PgPool>) -> Self {
+        let read_pool = read_pool.unwrap_or_else(|| write_pool.clone());
+        Self {
+            write_pool,
+            read_pool,
+        }
+    }
+
+    fn parse_mapping(row: &PgRow) -> DbResult<ClientCertMapping> {
+        Ok(ClientCertMapping {
+            id: row.get("id"),
+            org_id: row.get("org_id"),
+            service_account_id: row.get("service_account_id"),
+            match_type: row
+                .get::<String, _>("match_type")
+                .parse()
+                .map_err(DbError::Internal)?,
+            match_value: row.get("match_value"),
+            description: row.get("description"),
+            created_at: row.get("created_at"),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl ClientCertMappingRepo for PostgresClientCertMappingRepo {
+    async fn create(
+        &self,
+        org_id: Uuid,
+        service_account_id: Uuid,
+        input: CreateClientCertMapping,
+    ) -> DbResult<ClientCertMapping> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+
+        sqlx::query(
+            r#"
+            INSERT INTO client_cert_mappings (
+                id, org_id, service_account_id, match_type, match_value, description, created_at
+            )
+            VALUES ($1, $2, $3, $4::cert_match_type, $5, $6, $7)
+            "#,
+        )
+        .bind(id)
+        .bind(org_id)
+        .bind(service_account_id)
+        .bind(input.match_type.as_str())
+        .bind(&input.match_value)
+        .bind(&input.description)
+        .bind(now)
+        .execute(&self.write_pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+                DbError::Conflict(format!(
+                    "A client certificate mapping for {} '{}' already exists",
+                    input.match_type.as_str(),
+                    input.match_value
+                ))
+            }
+            _ => DbError::from(e),
+        })?;
+
+        Ok(ClientCertMapping {
+            id,
+            org_id,
+            service_account_id,
+            match_type: input.match_type,
+            match_value: input.match_value,
+            description: input.description,
+            created_at: now,
+        })
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<ClientCertMapping>> {
+        let sql = format!("SELECT {COLUMNS} FROM client_cert_mappings m WHERE m.id = $1");
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .fetch_optional(&self.read_pool)
+            .await?;
+
+        row.map(|r| Self::parse_mapping(&r)).transpose()
+    }
+
+    async fn list_by_service_account(
+        &self,
+        service_account_id: Uuid,
+        params: ListParams,
+    ) -> DbResult<ListResult<ClientCertMapping>> {
+        let limit = params.limit.unwrap_or(100);
+        let fetch_limit = limit + 1;
+
+        let (comparison, order, should_reverse) =
+            params.sort_order.cursor_query_params(params.direction);
+        let (cursor_clause, limit_idx, order, should_reverse) = if params.cursor.is_some() {
+            (
+                format!("AND ROW(m.created_at, m.id) {} ROW($2, $3)", comparison),
+                4,
+                order,
+                should_reverse,
+            )
+        } else {
+            (String::new(), 2, params.sort_order.as_sql(), false)
+        };
+
+        let sql = format!(
+            r#"
+            SELECT {COLUMNS}
+            FROM client_cert_mappings m
+            WHERE m.service_account_id = $1 {cursor_clause}
+            ORDER BY m.created_at {order}, m.id {order}
+            LIMIT ${limit_idx}
+            "#
+        );
+
+        let mut q = sqlx::query(&sql).bind(service_account_id);
+        if let Some(ref cursor) = params.cursor {
+            q = q.bind(cursor.created_at).bind(cursor.id);
+        }
+        let rows = q.bind(fetch_limit).fetch_all(&self.read_pool).await?;
+
+        let has_more = rows.len() as i64 > limit;
+        let mut items = rows
+            .iter()
+            .take(limit as usize)
+            .map(Self::parse_mapping)
+            .collect::<DbResult<Vec<_>>>()?;
+
+        if should_reverse {
+            items.reverse();
+        }
+
+        let direction = if params.cursor.is_some() {
+            params.direction
+        } else {
+            CursorDirection::Forward
+        };
+        let cursors =
+            PageCursors::from_items(&items, has_more, direction, params.cursor.as_ref(), |m| {
+                cursor_from_row(m.created_at, m.id)
+            });
+
+        Ok(ListResult::new(items, has_more, cursors))
+    }
+
+    async fn find_by_identifiers(
+        &self,
+        identifiers: &[(CertMatchType, String)],
+    ) -> DbResult<Vec<ClientCertMapping>> {
+        if identifiers.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let (types, values): (Vec<&str>, Vec<String>) = identifiers
+            .iter()
+            .map(|(t, v)| (t.as_str(), v.clone()))
+            .unzip();
+
+        // Pair the two arrays positionally so each value is only matched
+        // against the certificate field it was extracted from.
+        let sql = format!(
+            r#"
+            SELECT {COLUMNS}
+            FROM client_cert_mappings m
+            JOIN service_accounts sa ON sa.id = m.service_account_id
+            JOIN UNNEST($1::TEXT[], $2::TEXT[]) AS ids(match_type, match_value)
+                ON m.match_type = ids.match_type::cert_match_type
+               AND m.match_value = ids.match_value
+            WHERE sa.deleted_at IS NULL
+            "#
+        );
+        let rows = sqlx::query(&sql)
+            .bind(&types)
+            .bind(&values)
+            .fetch_all(&self.read_pool)
+            .await?;
+
+        rows.iter().map(Self::parse_mapping).collect()
+    }
+
+    async fn delete(&self, id: Uuid) -> DbResult<()> {
+        let result = sqlx::query("DELETE FROM client_cert_mappings WHERE id = $1")
+            .bind(id)
+            .execute(&self.write_pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        Ok(())
+    }
+}
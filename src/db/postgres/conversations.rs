@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use sqlx::{PgPool, Row};
+use sqlx::{PgPool, Row, postgres::PgRow};
 use uuid::Uuid;
 
 use crate::{
@@ -9,8 +9,9 @@ use crate::{
         repos::{ConversationRepo, Cursor, CursorDirection, ListParams, ListResult, PageCursors},
     },
     models::{
-        AppendMessages, Conversation, ConversationOwnerType, ConversationWithProject,
-        CreateConversation, Message, UpdateConversation,
+        AppendMessages, Conversation, ConversationBranch, ConversationOwnerType,
+        ConversationWithProject, CreateConversation, ForkConversation, Message,
+        SetConversationSummary, UpdateConversation,
     },
 };
 
@@ -36,6 +37,19 @@ impl PostgresConversationRepo {
         serde_json::from_value(json_value).map_err(|e| DbError::Internal(e.to_string()))
     }
 
+    fn parse_branch(row: &PgRow) -> DbResult<ConversationBranch> {
+        Ok(ConversationBranch {
+            id: row.get("id"),
+            conversation_id: row.get("conversation_id"),
+            parent_branch_id: row.get("parent_branch_id"),
+            fork_index: row.get("fork_index"),
+            messages: Self::parse_messages(row.get("messages"))?,
+            is_active: row.get("is_active"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        })
+    }
+
     /// Create a cursor from a conversation's updated_at and id.
     ///
     /// Note: We use updated_at instead of created_at because conversations
@@ -67,7 +81,7 @@ impl PostgresConversationRepo {
 
         let query = format!(
             r#"
-            SELECT id, owner_type::TEXT, owner_id, title, models, messages, pin_order, created_at, updated_at
+            SELECT id, owner_type::TEXT, owner_id, title, models, messages, pin_order, summary, created_at, updated_at
             FROM conversations
             WHERE owner_type = $1::conversation_owner_type AND owner_id = $2
             AND ROW(updated_at, id) {} ROW($3, $4)
@@ -104,6 +118,7 @@ impl PostgresConversationRepo {
                     models: Self::parse_models(row.get("models"))?,
                     messages: Self::parse_messages(row.get("messages"))?,
                     pin_order: row.get("pin_order"),
+                    summary: row.get("summary"),
                     created_at: row.get("created_at"),
                     updated_at: row.get("updated_at"),
                 })
@@ -142,7 +157,7 @@ impl ConversationRepo for PostgresConversationRepo {
             r#"
             INSERT INTO conversations (id, owner_type, owner_id, title, models, messages, pin_order)
             VALUES ($1, $2::conversation_owner_type, $3, $4, $5, $6, NULL)
-            RETURNING id, owner_type::TEXT, owner_id, title, models, messages, pin_order, created_at, updated_at
+            RETURNING id, owner_type::TEXT, owner_id, title, models, messages, pin_order, summary, created_at, updated_at
             "#,
         )
         .bind(Uuid::new_v4())
@@ -166,6 +181,7 @@ impl ConversationRepo for PostgresConversationRepo {
             models: Self::parse_models(row.get("models"))?,
             messages: Self::parse_messages(row.get("messages"))?,
             pin_order: row.get("pin_order"),
+            summary: row.get("summary"),
             created_at: row.get("created_at"),
             updated_at: row.get("updated_at"),
         })
@@ -174,7 +190,7 @@ impl ConversationRepo for PostgresConversationRepo {
     async fn get_by_id(&self, id: Uuid) -> DbResult<Option<Conversation>> {
         let result = sqlx::query(
             r#"
-            SELECT id, owner_type::TEXT, owner_id, title, models, messages, pin_order, created_at, updated_at
+            SELECT id, owner_type::TEXT, owner_id, title, models, messages, pin_order, summary, created_at, updated_at
             FROM conversations
             WHERE id = $1 AND deleted_at IS NULL
             "#,
@@ -197,6 +213,7 @@ impl ConversationRepo for PostgresConversationRepo {
                     models: Self::parse_models(row.get("models"))?,
                     messages: Self::parse_messages(row.get("messages"))?,
                     pin_order: row.get("pin_order"),
+                    summary: row.get("summary"),
                     created_at: row.get("created_at"),
                     updated_at: row.get("updated_at"),
                 }))
@@ -208,7 +225,7 @@ impl ConversationRepo for PostgresConversationRepo {
     async fn get_by_id_and_org(&self, id: Uuid, org_id: Uuid) -> DbResult<Option<Conversation>> {
         let result = sqlx::query(
             r#"
-            SELECT c.id, c.owner_type::TEXT, c.owner_id, c.title, c.models, c.messages, c.pin_order, c.created_at, c.updated_at
+            SELECT c.id, c.owner_type::TEXT, c.owner_id, c.title, c.models, c.messages, c.pin_order, c.summary, c.created_at, c.updated_at
             FROM conversations c
             WHERE c.id = $1 AND c.deleted_at IS NULL
             AND (
@@ -242,6 +259,7 @@ impl ConversationRepo for PostgresConversationRepo {
                     models: Self::parse_models(row.get("models"))?,
                     messages: Self::parse_messages(row.get("messages"))?,
                     pin_order: row.get("pin_order"),
+                    summary: row.get("summary"),
                     created_at: row.get("created_at"),
                     updated_at: row.get("updated_at"),
                 }))
@@ -270,7 +288,7 @@ impl ConversationRepo for PostgresConversationRepo {
         // First page (no cursor provided)
         let query = if params.include_deleted {
             r#"
-            SELECT id, owner_type::TEXT, owner_id, title, models, messages, pin_order, created_at, updated_at
+            SELECT id, owner_type::TEXT, owner_id, title, models, messages, pin_order, summary, created_at, updated_at
             FROM conversations
             WHERE owner_type = $1::conversation_owner_type AND owner_id = $2
             ORDER BY updated_at DESC, id DESC
@@ -278,7 +296,7 @@ impl ConversationRepo for PostgresConversationRepo {
             "#
         } else {
             r#"
-            SELECT id, owner_type::TEXT, owner_id, title, models, messages, pin_order, created_at, updated_at
+            SELECT id, owner_type::TEXT, owner_id, title, models, messages, pin_order, summary, created_at, updated_at
             FROM conversations
             WHERE owner_type = $1::conversation_owner_type AND owner_id = $2 AND deleted_at IS NULL
             ORDER BY updated_at DESC, id DESC
@@ -310,6 +328,7 @@ impl ConversationRepo for PostgresConversationRepo {
                     models: Self::parse_models(row.get("models"))?,
                     messages: Self::parse_messages(row.get("messages"))?,
                     pin_order: row.get("pin_order"),
+                    summary: row.get("summary"),
                     created_at: row.get("created_at"),
                     updated_at: row.get("updated_at"),
                 })
@@ -355,7 +374,7 @@ impl ConversationRepo for PostgresConversationRepo {
         // Lock the row for update to prevent concurrent modifications
         let current_row = sqlx::query(
             r#"
-            SELECT id, owner_type::TEXT, owner_id, title, models, messages, pin_order, created_at, updated_at
+            SELECT id, owner_type::TEXT, owner_id, title, models, messages, pin_order, summary, created_at, updated_at
             FROM conversations
             WHERE id = $1 AND deleted_at IS NULL
             FOR UPDATE
@@ -389,6 +408,7 @@ impl ConversationRepo for PostgresConversationRepo {
             .map(|m| serde_json::to_value(&m).map_err(|e| DbError::Internal(e.to_string())))
             .transpose()?
             .unwrap_or(current_models);
+        let messages_replaced = input.messages.is_some();
         let new_messages = input
             .messages
             .map(|m| serde_json::to_value(&m).map_err(|e| DbError::Internal(e.to_string())))
@@ -400,7 +420,7 @@ impl ConversationRepo for PostgresConversationRepo {
             UPDATE conversations
             SET owner_type = $1::conversation_owner_type, owner_id = $2, title = $3, models = $4, messages = $5, updated_at = NOW()
             WHERE id = $6 AND deleted_at IS NULL
-            RETURNING id, owner_type::TEXT, owner_id, title, models, messages, pin_order, created_at, updated_at
+            RETURNING id, owner_type::TEXT, owner_id, title, models, messages, pin_order, summary, created_at, updated_at
             "#,
         )
         .bind(new_owner_type.as_str())
@@ -413,6 +433,21 @@ impl ConversationRepo for PostgresConversationRepo {
         .await?
         .ok_or(DbError::NotFound)?;
 
+        // Keep the active branch (if the conversation has been forked) in step
+        if messages_replaced {
+            sqlx::query(
+                r#"
+                UPDATE conversation_branches
+                SET messages = $1, updated_at = NOW()
+                WHERE conversation_id = $2 AND is_active
+                "#,
+            )
+            .bind(&new_messages)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
         tx.commit().await?;
 
         Ok(Conversation {
@@ -423,6 +458,7 @@ impl ConversationRepo for PostgresConversationRepo {
             models: Self::parse_models(row.get("models"))?,
             messages: Self::parse_messages(row.get("messages"))?,
             pin_order,
+            summary: row.get("summary"),
             created_at: row.get("created_at"),
             updated_at: row.get("updated_at"),
         })
@@ -433,6 +469,8 @@ impl ConversationRepo for PostgresConversationRepo {
         let new_messages_json =
             serde_json::to_value(&input.messages).map_err(|e| DbError::Internal(e.to_string()))?;
 
+        let mut tx = self.write_pool.begin().await?;
+
         let row = sqlx::query(
             r#"
             UPDATE conversations
@@ -443,11 +481,27 @@ impl ConversationRepo for PostgresConversationRepo {
         )
         .bind(&new_messages_json)
         .bind(id)
-        .fetch_optional(&self.write_pool)
+        .fetch_optional(&mut *tx)
         .await?
         .ok_or(DbError::NotFound)?;
+        let messages: serde_json::Value = row.get("messages");
+
+        // Keep the active branch (if the conversation has been forked) in step
+        sqlx::query(
+            r#"
+            UPDATE conversation_branches
+            SET messages = $1, updated_at = NOW()
+            WHERE conversation_id = $2 AND is_active
+            "#,
+        )
+        .bind(&messages)
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
 
-        Self::parse_messages(row.get("messages"))
+        Self::parse_messages(messages)
     }
 
     async fn delete(&self, id: Uuid) -> DbResult<()> {
@@ -497,6 +551,7 @@ impl ConversationRepo for PostgresConversationRepo {
                 c.models,
                 c.messages,
                 c.pin_order,
+                c.summary,
                 c.created_at,
                 c.updated_at,
                 NULL::UUID as project_id,
@@ -516,6 +571,7 @@ impl ConversationRepo for PostgresConversationRepo {
                 c.models,
                 c.messages,
                 c.pin_order,
+                c.summary,
                 c.created_at,
                 c.updated_at,
                 p.id as project_id,
@@ -558,6 +614,7 @@ impl ConversationRepo for PostgresConversationRepo {
                         models: Self::parse_models(row.get("models"))?,
                         messages: Self::parse_messages(row.get("messages"))?,
                         pin_order: row.get("pin_order"),
+                        summary: row.get("summary"),
                         created_at: row.get("created_at"),
                         updated_at: row.get("updated_at"),
                     },
@@ -577,7 +634,7 @@ impl ConversationRepo for PostgresConversationRepo {
             UPDATE conversations
             SET pin_order = $1, updated_at = NOW()
             WHERE id = $2 AND deleted_at IS NULL
-            RETURNING id, owner_type::TEXT, owner_id, title, models, messages, pin_order, created_at, updated_at
+            RETURNING id, owner_type::TEXT, owner_id, title, models, messages, pin_order, summary, created_at, updated_at
             "#,
         )
         .bind(pin_order)
@@ -598,6 +655,194 @@ impl ConversationRepo for PostgresConversationRepo {
             models: Self::parse_models(row.get("models"))?,
             messages: Self::parse_messages(row.get("messages"))?,
             pin_order: row.get("pin_order"),
+            summary: row.get("summary"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        })
+    }
+
+    // ==================== Branching ====================
+
+    async fn list_branches(&self, id: Uuid) -> DbResult<Vec<ConversationBranch>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, conversation_id, parent_branch_id, fork_index, messages, is_active, created_at, updated_at
+            FROM conversation_branches
+            WHERE conversation_id = $1
+            -- The root branch lists first even if forked within the same millisecond
+            ORDER BY created_at ASC, parent_branch_id IS NOT NULL, id ASC
+            "#,
+        )
+        .bind(id)
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        rows.iter().map(Self::parse_branch).collect()
+    }
+
+    async fn fork(&self, id: Uuid, input: ForkConversation) -> DbResult<ConversationBranch> {
+        // Lock the conversation so concurrent forks and appends can't interleave
+        // between reading the history and switching the active branch
+        let mut tx = self.write_pool.begin().await?;
+
+        let current_row = sqlx::query(
+            r#"
+            SELECT messages, created_at
+            FROM conversations
+            WHERE id = $1 AND deleted_at IS NULL
+            FOR UPDATE
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(DbError::NotFound)?;
+
+        let current_messages: serde_json::Value = current_row.get("messages");
+        let conversation_created_at: DateTime<Utc> = current_row.get("created_at");
+        let mut messages = Self::parse_messages(current_messages.clone())?;
+
+        let fork_index = input.message_index as usize;
+        if fork_index >= messages.len() {
+            return Err(DbError::Validation(format!(
+                "message_index {} is out of range for a conversation with {} messages",
+                input.message_index,
+                messages.len()
+            )));
+        }
+
+        let active_branch_id: Option<Uuid> = sqlx::query_scalar(
+            r#"
+            UPDATE conversation_branches
+            SET is_active = FALSE
+            WHERE conversation_id = $1 AND is_active
+            RETURNING id
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let parent_branch_id = match active_branch_id {
+            Some(parent_id) => parent_id,
+            None => {
+                // First fork: record the pre-fork history as the root branch.
+                // It takes the conversation's creation time so it always lists first.
+                let root_id = Uuid::new_v4();
+                sqlx::query(
+                    r#"
+                    INSERT INTO conversation_branches (id, conversation_id, parent_branch_id, fork_index, messages, is_active, created_at, updated_at)
+                    VALUES ($1, $2, NULL, 0, $3, FALSE, $4, NOW())
+                    "#,
+                )
+                .bind(root_id)
+                .bind(id)
+                .bind(&current_messages)
+                .bind(conversation_created_at)
+                .execute(&mut *tx)
+                .await?;
+                root_id
+            }
+        };
+
+        messages.truncate(fork_index);
+        messages.extend(input.message);
+        let messages_json =
+            serde_json::to_value(&messages).map_err(|e| DbError::Internal(e.to_string()))?;
+
+        let row = sqlx::query(
+            r#"
+            INSERT INTO conversation_branches (id, conversation_id, parent_branch_id, fork_index, messages, is_active, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, TRUE, NOW(), NOW())
+            RETURNING id, conversation_id, parent_branch_id, fork_index, messages, is_active, created_at, updated_at
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(id)
+        .bind(parent_branch_id)
+        .bind(fork_index as i32)
+        .bind(&messages_json)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE conversations
+            SET messages = $1, updated_at = NOW()
+            WHERE id = $2 AND deleted_at IS NULL
+            "#,
+        )
+        .bind(&messages_json)
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Self::parse_branch(&row)
+    }
+
+    async fn switch_branch(&self, id: Uuid, branch_id: Uuid) -> DbResult<Conversation> {
+        let mut tx = self.write_pool.begin().await?;
+
+        sqlx::query("SELECT id FROM conversations WHERE id = $1 AND deleted_at IS NULL FOR UPDATE")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or(DbError::NotFound)?;
+
+        let messages: serde_json::Value = sqlx::query_scalar(
+            "SELECT messages FROM conversation_branches WHERE id = $1 AND conversation_id = $2",
+        )
+        .bind(branch_id)
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(DbError::NotFound)?;
+
+        // Deactivate first so the one-active-branch unique index is never violated
+        sqlx::query(
+            r#"
+            UPDATE conversation_branches
+            SET is_active = FALSE
+            WHERE conversation_id = $1 AND is_active
+            "#,
+        )
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query("UPDATE conversation_branches SET is_active = TRUE WHERE id = $1")
+            .bind(branch_id)
+            .execute(&mut *tx)
+            .await?;
+
+        let row = sqlx::query(
+            r#"
+            UPDATE conversations
+            SET messages = $1, updated_at = NOW()
+            WHERE id = $2 AND deleted_at IS NULL
+            RETURNING id, owner_type::TEXT, owner_id, title, models, messages, pin_order, summary, created_at, updated_at
+            "#,
+        )
+        .bind(&messages)
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        let owner_type_str: String = row.get("owner_type");
+        Ok(Conversation {
+            id: row.get("id"),
+            owner_type: owner_type_str
+                .parse()
+                .map_err(|e: String| DbError::Internal(e))?,
+            owner_id: row.get("owner_id"),
+            title: row.get("title"),
+            models: Self::parse_models(row.get("models"))?,
+            messages: Self::parse_messages(row.get("messages"))?,
+            pin_order: row.get("pin_order"),
+            summary: row.get("summary"),
             created_at: row.get("created_at"),
             updated_at: row.get("updated_at"),
         })
@@ -605,6 +850,85 @@ impl ConversationRepo for PostgresConversationRepo {
 
     // ==================== Retention Operations ====================
 
+    async fn list_needing_summary(
+        &self,
+        org_id: Option<Uuid>,
+        min_messages: u32,
+        resummarize_after: u32,
+        limit: i64,
+    ) -> DbResult<Vec<Conversation>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT c.id, c.owner_type::TEXT, c.owner_id, c.title, c.models, c.messages, c.pin_order, c.summary, c.created_at, c.updated_at
+            FROM conversations c
+            WHERE c.deleted_at IS NULL
+              AND (
+                  ($1::UUID IS NULL AND c.owner_type = 'user'::conversation_owner_type)
+                  OR (c.owner_type = 'project'::conversation_owner_type AND c.owner_id IN (SELECT id FROM projects WHERE org_id = $1))
+              )
+              AND jsonb_array_length(c.messages) >= $2
+              AND (
+                  c.summary IS NULL
+                  OR jsonb_array_length(c.messages) < c.summary_message_count
+                  OR jsonb_array_length(c.messages) - c.summary_message_count >= $3
+              )
+            ORDER BY c.updated_at DESC, c.id DESC
+            LIMIT $4
+            "#,
+        )
+        .bind(org_id)
+        .bind(min_messages as i32)
+        .bind(resummarize_after.max(1) as i32)
+        .bind(limit)
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                let owner_type_str: String = row.get("owner_type");
+
+                Ok(Conversation {
+                    id: row.get("id"),
+                    owner_type: owner_type_str
+                        .parse()
+                        .map_err(|e: String| DbError::Internal(e))?,
+                    owner_id: row.get("owner_id"),
+                    title: row.get("title"),
+                    models: Self::parse_models(row.get("models"))?,
+                    messages: Self::parse_messages(row.get("messages"))?,
+                    pin_order: row.get("pin_order"),
+                    summary: row.get("summary"),
+                    created_at: row.get("created_at"),
+                    updated_at: row.get("updated_at"),
+                })
+            })
+            .collect()
+    }
+
+    async fn set_summary(&self, id: Uuid, input: SetConversationSummary) -> DbResult<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE conversations
+            SET summary = $1,
+                summary_message_count = $2,
+                title = CASE WHEN $3::TEXT IS NOT NULL AND title = $4 THEN $3 ELSE title END
+            WHERE id = $5 AND deleted_at IS NULL
+            "#,
+        )
+        .bind(&input.summary)
+        .bind(input.message_count)
+        .bind(&input.title)
+        .bind(&input.expected_title)
+        .bind(id)
+        .execute(&self.write_pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+        Ok(())
+    }
+
     async fn hard_delete_soft_deleted_before(
         &self,
         cutoff: DateTime<Utc>,
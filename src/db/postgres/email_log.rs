@@ -0,0 +1,228 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Row, postgres::PgRow};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{
+            CursorDirection, EmailLogRepo, ListParams, ListResult, PageCursors, cursor_from_row,
+            truncate_to_millis,
+        },
+    },
+    models::{EmailLogEntry, EmailLogFilter, NewEmailLogEntry},
+};
+
+const COLUMNS: &str =
+    "id, org_id, kind, from_address, recipients, subject, status, error, created_at";
+
+pub struct PostgresEmailLogRepo {
+    write_pool: PgPool,
+    read_pool: PgPool,
+}
+
+impl PostgresEmailLogRepo {
+    pub fn new(write_pool: PgPool, read_pool: Option<PgPool>) -> Self {
+        let read_pool = read_pool.unwrap_or_else(|| write_pool.clone());
+        Self {
+            write_pool,
+            read_pool,
+        }
+    }
+
+    fn parse_entry(row: &PgRow) -> DbResult<EmailLogEntry> {
+        Ok(EmailLogEntry {
+            id: row.get("id"),
+            org_id: row.get("org_id"),
+            kind: row
+                .get::<String, _>("kind")
+                .parse()
+                .map_err(DbError::Internal)?,
+            from_address: row.get("from_address"),
+            recipients: serde_json::from_value(row.get("recipients"))?,
+            subject: row.get("subject"),
+            status: row
+                .get::<String, _>("status")
+                .parse()
+                .map_err(DbError::Internal)?,
+            error: row.get("error"),
+            created_at: row.get("created_at"),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl EmailLogRepo for PostgresEmailLogRepo {
+    async fn create(&self, input: NewEmailLogEntry) -> DbResult<EmailLogEntry> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+
+        sqlx::query(
+            r#"
+            INSERT INTO email_send_log (
+                id, org_id, kind, from_address, recipients, subject, status, error, created_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#,
+        )
+        .bind(id)
+        .bind(input.org_id)
+        .bind(input.kind.as_str())
+        .bind(&input.from_address)
+        .bind(serde_json::json!(input.recipients))
+        .bind(&input.subject)
+        .bind(input.status.as_str())
+        .bind(&input.error)
+        .bind(now)
+        .execute(&self.write_pool)
+        .await?;
+
+        Ok(EmailLogEntry {
+            id,
+            org_id: input.org_id,
+            kind: input.kind,
+            from_address: input.from_address,
+            recipients: input.recipients,
+            subject: input.subject,
+            status: input.status,
+            error: input.error,
+            created_at: now,
+        })
+    }
+
+    async fn list(
+        &self,
+        filter: EmailLogFilter,
+        params: ListParams,
+    ) -> DbResult<ListResult<EmailLogEntry>> {
+        let limit = params.limit.unwrap_or(100);
+        let fetch_limit = limit + 1;
+
+        let mut conditions = Vec::new();
+        let mut idx = 1;
+        if filter.org_id.is_some() {
+            conditions.push(format!("org_id = ${idx}"));
+            idx += 1;
+        }
+        if filter.kind.is_some() {
+            conditions.push(format!("kind = ${idx}"));
+            idx += 1;
+        }
+        if filter.status.is_some() {
+            conditions.push(format!("status = ${idx}"));
+            idx += 1;
+        }
+
+        let (comparison, order, should_reverse) =
+            params.sort_order.cursor_query_params(params.direction);
+        let (order, should_reverse) = if params.cursor.is_some() {
+            conditions.push(format!(
+                "ROW(created_at, id) {} ROW(${}, ${})",
+                comparison,
+                idx,
+                idx + 1
+            ));
+            idx += 2;
+            (order, should_reverse)
+        } else {
+            (params.sort_order.as_sql(), false)
+        };
+
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+        let sql = format!(
+            r#"
+            SELECT {COLUMNS}
+            FROM email_send_log
+            {where_clause}
+            ORDER BY created_at {order}, id {order}
+            LIMIT ${idx}
+            "#
+        );
+
+        let mut q = sqlx::query(&sql);
+        if let Some(org_id) = filter.org_id {
+            q = q.bind(org_id);
+        }
+        if let Some(kind) = filter.kind {
+            q = q.bind(kind.as_str());
+        }
+        if let Some(status) = filter.status {
+            q = q.bind(status.as_str());
+        }
+        if let Some(ref cursor) = params.cursor {
+            q = q.bind(cursor.created_at).bind(cursor.id);
+        }
+        let rows = q.bind(fetch_limit).fetch_all(&self.read_pool).await?;
+
+        let has_more = rows.len() as i64 > limit;
+        let mut items = rows
+            .iter()
+            .take(limit as usize)
+            .map(Self::parse_entry)
+            .collect::<DbResult<Vec<_>>>()?;
+
+        if should_reverse {
+            items.reverse();
+        }
+
+        let direction = if params.cursor.is_some() {
+            params.direction
+        } else {
+            CursorDirection::Forward
+        };
+        let cursors =
+            PageCursors::from_items(&items, has_more, direction, params.cursor.as_ref(), |e| {
+                cursor_from_row(e.created_at, e.id)
+            });
+
+        Ok(ListResult::new(items, has_more, cursors))
+    }
+
+    async fn delete_before(
+        &self,
+        cutoff: DateTime<Utc>,
+        batch_size: u32,
+        max_deletes: u64,
+    ) -> DbResult<u64> {
+        let mut total_deleted: u64 = 0;
+
+        loop {
+            if total_deleted >= max_deletes {
+                break;
+            }
+
+            let remaining = max_deletes - total_deleted;
+            let limit = std::cmp::min(batch_size as u64, remaining) as i64;
+
+            let result = sqlx::query(
+                r#"
+                DELETE FROM email_send_log
+                WHERE ctid IN (
+                    SELECT ctid FROM email_send_log
+                    WHERE created_at < $1
+                    LIMIT $2
+                )
+                "#,
+            )
+            .bind(cutoff)
+            .bind(limit)
+            .execute(&self.write_pool)
+            .await?;
+
+            let rows_deleted = result.rows_affected();
+            total_deleted += rows_deleted;
+
+            if rows_deleted < limit as u64 {
+                break;
+            }
+        }
+
+        Ok(total_deleted)
+    }
+}
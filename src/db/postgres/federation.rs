@@ -0,0 +1,201 @@
+use std::collections::BTreeSet;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Row, postgres::PgRow};
+
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{DateRange, FederationRepo, truncate_to_millis},
+    },
+    models::{FederatedGateway, FederatedUsage, FederatedUsageRow, FederationReport},
+};
+
+pub struct PostgresFederationRepo {
+    write_pool: PgPool,
+    read_pool: PgPool,
+}
+
+impl PostgresFederationRepo {
+    pub fn new(write_pool: PgPool, read_pool: Option<PgPool>) -> Self {
+        let read_pool = read_pool.unwrap_or_else(|| write_pool.clone());
+        Self {
+            write_pool,
+            read_pool,
+        }
+    }
+
+    fn parse_gateway(row: &PgRow) -> DbResult<FederatedGateway> {
+        Ok(FederatedGateway {
+            id: row.get("id"),
+            region: row.get("region"),
+            version: row.get("version"),
+            providers: serde_json::from_value(row.get::<serde_json::Value, _>("providers"))
+                .map_err(|e| {
+                    DbError::Internal(format!("failed to deserialize federated providers: {e}"))
+                })?,
+            first_report_at: row.get("first_report_at"),
+            last_report_at: row.get("last_report_at"),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl FederationRepo for PostgresFederationRepo {
+    async fn record_report(
+        &self,
+        report: &FederationReport,
+        received_at: DateTime<Utc>,
+    ) -> DbResult<()> {
+        let now = truncate_to_millis(received_at);
+        let providers = serde_json::to_value(&report.providers)?;
+
+        let mut tx = self.write_pool.begin().await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO federated_gateways (
+                id, region, version, providers, first_report_at, last_report_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $5)
+            ON CONFLICT (id) DO UPDATE SET
+                region = EXCLUDED.region,
+                version = EXCLUDED.version,
+                providers = EXCLUDED.providers,
+                last_report_at = EXCLUDED.last_report_at
+            "#,
+        )
+        .bind(&report.gateway_id)
+        .bind(&report.region)
+        .bind(&report.version)
+        .bind(&providers)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+
+        let dates: Vec<_> = report
+            .usage
+            .iter()
+            .map(|u| u.date)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        sqlx::query("DELETE FROM federated_usage WHERE gateway_id = $1 AND date = ANY($2)")
+            .bind(&report.gateway_id)
+            .bind(&dates)
+            .execute(&mut *tx)
+            .await?;
+
+        for usage in &report.usage {
+            sqlx::query(
+                r#"
+                INSERT INTO federated_usage (
+                    gateway_id, date, model, total_cost_microcents, input_tokens,
+                    output_tokens, total_tokens, request_count, updated_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                ON CONFLICT (gateway_id, date, model) DO UPDATE SET
+                    total_cost_microcents = EXCLUDED.total_cost_microcents,
+                    input_tokens = EXCLUDED.input_tokens,
+                    output_tokens = EXCLUDED.output_tokens,
+                    total_tokens = EXCLUDED.total_tokens,
+                    request_count = EXCLUDED.request_count,
+                    updated_at = EXCLUDED.updated_at
+                "#,
+            )
+            .bind(&report.gateway_id)
+            .bind(usage.date)
+            .bind(&usage.model)
+            .bind(usage.total_cost_microcents)
+            .bind(usage.input_tokens)
+            .bind(usage.output_tokens)
+            .bind(usage.total_tokens)
+            .bind(usage.request_count)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn list_gateways(&self) -> DbResult<Vec<FederatedGateway>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, region, version, providers, first_report_at, last_report_at
+            FROM federated_gateways
+            ORDER BY id ASC
+            "#,
+        )
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        rows.iter().map(Self::parse_gateway).collect()
+    }
+
+    async fn get_gateway(&self, id: &str) -> DbResult<Option<FederatedGateway>> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, region, version, providers, first_report_at, last_report_at
+            FROM federated_gateways
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.read_pool)
+        .await?;
+
+        row.as_ref().map(Self::parse_gateway).transpose()
+    }
+
+    async fn delete_gateway(&self, id: &str) -> DbResult<bool> {
+        // Usage rows go with the gateway via ON DELETE CASCADE.
+        let result = sqlx::query("DELETE FROM federated_gateways WHERE id = $1")
+            .bind(id)
+            .execute(&self.write_pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list_usage(
+        &self,
+        range: DateRange,
+        gateway_id: Option<&str>,
+    ) -> DbResult<Vec<FederatedUsageRow>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT gateway_id, date, model, total_cost_microcents, input_tokens,
+                   output_tokens, total_tokens, request_count
+            FROM federated_usage
+            WHERE date >= $1 AND date <= $2
+              AND ($3::VARCHAR IS NULL OR gateway_id = $3)
+            ORDER BY date ASC, gateway_id ASC, model ASC
+            "#,
+        )
+        .bind(range.start)
+        .bind(range.end)
+        .bind(gateway_id)
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| FederatedUsageRow {
+                gateway_id: row.get("gateway_id"),
+                usage: FederatedUsage {
+                    date: row.get("date"),
+                    model: row.get("model"),
+                    total_cost_microcents: row.get("total_cost_microcents"),
+                    input_tokens: row.get("input_tokens"),
+                    output_tokens: row.get("output_tokens"),
+                    total_tokens: row.get("total_tokens"),
+                    request_count: row.get("request_count"),
+                },
+            })
+            .collect())
+    }
+}
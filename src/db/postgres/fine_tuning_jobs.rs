@@ -0,0 +1,198 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::{PgPool, Row, postgres::PgRow};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{FineTuningJobRepo, truncate_to_millis},
+    },
+    models::{CreateFineTuningJobRecord, FineTuningJobRecord, FineTuningJobStatus},
+};
+
+const COLUMNS: &str = "id, provider, provider_job_id, org_id, project_id, team_id, user_id, \
+                       api_key_id, service_account_id, model, training_file, validation_file, \
+                       status, fine_tuned_model, trained_tokens, completed_at, created_at, \
+                       updated_at";
+
+/// Provider statuses after which a job never changes again.
+const TERMINAL_STATUSES: &str = "('succeeded', 'failed', 'cancelled')";
+
+pub struct PostgresFineTuningJobRepo {
+    write_pool: PgPool,
+    read_pool: PgPool,
+}
+
+impl PostgresFineTuningJobRepo {
+    pub fn new(write_pool: PgPool, read_pool: Option<PgPool>) -> Self {
+        let read_pool = read_pool.unwrap_or_else(|| write_pool.clone());
+        Self {
+            write_pool,
+            read_pool,
+        }
+    }
+
+    fn parse_job(row: &PgRow) -> FineTuningJobRecord {
+        FineTuningJobRecord {
+            id: row.get("id"),
+            provider: row.get("provider"),
+            provider_job_id: row.get("provider_job_id"),
+            org_id: row.get("org_id"),
+            project_id: row.get("project_id"),
+            team_id: row.get("team_id"),
+            user_id: row.get("user_id"),
+            api_key_id: row.get("api_key_id"),
+            service_account_id: row.get("service_account_id"),
+            model: row.get("model"),
+            training_file: row.get("training_file"),
+            validation_file: row.get("validation_file"),
+            status: row.get("status"),
+            fine_tuned_model: row.get("fine_tuned_model"),
+            trained_tokens: row.get("trained_tokens"),
+            completed_at: row.get("completed_at"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl FineTuningJobRepo for PostgresFineTuningJobRepo {
+    async fn create(&self, input: CreateFineTuningJobRecord) -> DbResult<FineTuningJobRecord> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+
+        sqlx::query(
+            r#"
+            INSERT INTO fine_tuning_jobs (
+                id, provider, provider_job_id, org_id, project_id, team_id, user_id,
+                api_key_id, service_account_id, model, training_file, validation_file,
+                status, created_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+            "#,
+        )
+        .bind(id)
+        .bind(&input.provider)
+        .bind(&input.provider_job_id)
+        .bind(input.org_id)
+        .bind(input.project_id)
+        .bind(input.team_id)
+        .bind(input.user_id)
+        .bind(input.api_key_id)
+        .bind(input.service_account_id)
+        .bind(&input.model)
+        .bind(&input.training_file)
+        .bind(&input.validation_file)
+        .bind(&input.status)
+        .bind(now)
+        .bind(now)
+        .execute(&self.write_pool)
+        .await?;
+
+        Ok(FineTuningJobRecord {
+            id,
+            provider: input.provider,
+            provider_job_id: input.provider_job_id,
+            org_id: input.org_id,
+            project_id: input.project_id,
+            team_id: input.team_id,
+            user_id: input.user_id,
+            api_key_id: input.api_key_id,
+            service_account_id: input.service_account_id,
+            model: input.model,
+            training_file: input.training_file,
+            validation_file: input.validation_file,
+            status: input.status,
+            fine_tuned_model: None,
+            trained_tokens: None,
+            completed_at: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<FineTuningJobRecord>> {
+        let sql = format!("SELECT {COLUMNS} FROM fine_tuning_jobs WHERE id = $1");
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .fetch_optional(&self.read_pool)
+            .await?;
+
+        Ok(row.as_ref().map(Self::parse_job))
+    }
+
+    async fn list(
+        &self,
+        org_id: Uuid,
+        project_id: Option<Uuid>,
+        limit: i64,
+    ) -> DbResult<Vec<FineTuningJobRecord>> {
+        let sql = format!(
+            "SELECT {COLUMNS} FROM fine_tuning_jobs \
+             WHERE org_id = $1 AND ($2::UUID IS NULL OR project_id = $2) \
+             ORDER BY created_at DESC, id DESC LIMIT $3"
+        );
+        let rows = sqlx::query(&sql)
+            .bind(org_id)
+            .bind(project_id)
+            .bind(limit)
+            .fetch_all(&self.read_pool)
+            .await?;
+
+        Ok(rows.iter().map(Self::parse_job).collect())
+    }
+
+    async fn list_incomplete(&self, limit: i64) -> DbResult<Vec<FineTuningJobRecord>> {
+        let sql = format!(
+            "SELECT {COLUMNS} FROM fine_tuning_jobs WHERE completed_at IS NULL \
+             ORDER BY updated_at ASC LIMIT $1"
+        );
+        let rows = sqlx::query(&sql)
+            .bind(limit)
+            .fetch_all(&self.write_pool)
+            .await?;
+
+        Ok(rows.iter().map(Self::parse_job).collect())
+    }
+
+    async fn update_status(
+        &self,
+        id: Uuid,
+        status: &FineTuningJobStatus,
+    ) -> DbResult<FineTuningJobRecord> {
+        let now = truncate_to_millis(Utc::now());
+        let sql = format!(
+            "UPDATE fine_tuning_jobs SET status = $1, fine_tuned_model = $2, \
+             trained_tokens = $3, updated_at = $4 WHERE id = $5 RETURNING {COLUMNS}"
+        );
+        let row = sqlx::query(&sql)
+            .bind(&status.status)
+            .bind(&status.fine_tuned_model)
+            .bind(status.trained_tokens)
+            .bind(now)
+            .bind(id)
+            .fetch_optional(&self.write_pool)
+            .await?
+            .ok_or(DbError::NotFound)?;
+
+        Ok(Self::parse_job(&row))
+    }
+
+    async fn mark_completed(&self, id: Uuid) -> DbResult<bool> {
+        let now = truncate_to_millis(Utc::now());
+        let sql = format!(
+            "UPDATE fine_tuning_jobs SET completed_at = $1 \
+             WHERE id = $2 AND completed_at IS NULL AND status IN {TERMINAL_STATUSES}"
+        );
+        let result = sqlx::query(&sql)
+            .bind(now)
+            .bind(id)
+            .execute(&self.write_pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
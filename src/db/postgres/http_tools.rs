@@ -0,0 +1,171 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::{PgPool, Row, postgres::PgRow};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{HttpToolRepo, truncate_to_millis},
+    },
+    models::{CreateHttpTool, HttpTool, UpdateHttpTool},
+};
+
+const COLUMNS: &str = "id, org_id, name, description, url, headers, parameters, timeout_secs, \
+                       max_response_bytes, created_at, updated_at";
+
+pub struct PostgresHttpToolRepo {
+    write_pool: PgPool,
+    read_pool: PgPool,
+}
+
+impl PostgresHttpToolRepo {
+    pub fn new(write_pool: PgPool, read_pool: Option<PgPool>) -> Self {
+        let read_pool = read_pool.unwrap_or_else(|| write_pool.clone());
+        Self {
+            write_pool,
+            read_pool,
+        }
+    }
+
+    fn parse(row: &PgRow) -> DbResult<HttpTool> {
+        Ok(HttpTool {
+            id: row.get("id"),
+            org_id: row.get("org_id"),
+            name: row.get("name"),
+            description: row.get("description"),
+            url: row.get("url"),
+            headers: serde_json::from_value(row.get("headers"))?,
+            parameters: row.get("parameters"),
+            timeout_secs: row.get("timeout_secs"),
+            max_response_bytes: row.get("max_response_bytes"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        })
+    }
+
+    fn map_name_conflict(name: &str) -> impl FnOnce(sqlx::Error) -> DbError + '_ {
+        move |e| match e {
+            sqlx::Error::Database(db_err) if db_err.is_unique_violation() => DbError::Conflict(
+                format!("HTTP tool '{name}' already exists in this organization"),
+            ),
+            _ => DbError::from(e),
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl HttpToolRepo for PostgresHttpToolRepo {
+    async fn create(&self, org_id: Uuid, input: CreateHttpTool) -> DbResult<HttpTool> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+
+        sqlx::query(
+            r#"
+            INSERT INTO http_tools (
+                id, org_id, name, description, url, headers, parameters, timeout_secs,
+                max_response_bytes, created_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            "#,
+        )
+        .bind(id)
+        .bind(org_id)
+        .bind(&input.name)
+        .bind(&input.description)
+        .bind(&input.url)
+        .bind(serde_json::to_value(&input.headers)?)
+        .bind(&input.parameters)
+        .bind(input.timeout_secs)
+        .bind(input.max_response_bytes)
+        .bind(now)
+        .bind(now)
+        .execute(&self.write_pool)
+        .await
+        .map_err(Self::map_name_conflict(&input.name))?;
+
+        Ok(HttpTool {
+            id,
+            org_id,
+            name: input.name,
+            description: input.description,
+            url: input.url,
+            headers: input.headers,
+            parameters: input.parameters,
+            timeout_secs: input.timeout_secs,
+            max_response_bytes: input.max_response_bytes,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<HttpTool>> {
+        let sql = format!("SELECT {COLUMNS} FROM http_tools WHERE id = $1");
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .fetch_optional(&self.read_pool)
+            .await?;
+
+        row.as_ref().map(Self::parse).transpose()
+    }
+
+    async fn list_by_org(&self, org_id: Uuid) -> DbResult<Vec<HttpTool>> {
+        let sql = format!("SELECT {COLUMNS} FROM http_tools WHERE org_id = $1 ORDER BY name ASC");
+        let rows = sqlx::query(&sql)
+            .bind(org_id)
+            .fetch_all(&self.read_pool)
+            .await?;
+
+        rows.iter().map(Self::parse).collect()
+    }
+
+    async fn update(&self, id: Uuid, input: UpdateHttpTool) -> DbResult<HttpTool> {
+        let headers = input
+            .headers
+            .as_ref()
+            .map(serde_json::to_value)
+            .transpose()?;
+        let name = input.name.clone().unwrap_or_default();
+        let sql = format!(
+            "UPDATE http_tools SET \
+                name = COALESCE($1, name), \
+                description = COALESCE($2, description), \
+                url = COALESCE($3, url), \
+                headers = COALESCE($4, headers), \
+                parameters = COALESCE($5, parameters), \
+                timeout_secs = COALESCE($6, timeout_secs), \
+                max_response_bytes = COALESCE($7, max_response_bytes), \
+                updated_at = $8 \
+             WHERE id = $9 \
+             RETURNING {COLUMNS}"
+        );
+        let row = sqlx::query(&sql)
+            .bind(&input.name)
+            .bind(&input.description)
+            .bind(&input.url)
+            .bind(headers)
+            .bind(&input.parameters)
+            .bind(input.timeout_secs)
+            .bind(input.max_response_bytes)
+            .bind(truncate_to_millis(Utc::now()))
+            .bind(id)
+            .fetch_optional(&self.write_pool)
+            .await
+            .map_err(Self::map_name_conflict(&name))?
+            .ok_or(DbError::NotFound)?;
+
+        Self::parse(&row)
+    }
+
+    async fn delete(&self, id: Uuid) -> DbResult<()> {
+        let result = sqlx::query("DELETE FROM http_tools WHERE id = $1")
+            .bind(id)
+            .execute(&self.write_pool)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Row, postgres::PgRow};
+
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{IdempotencyKeyRepo, truncate_to_millis},
+    },
+    models::{IdempotencyRecord, IdempotencyStatus, IdempotentResponse},
+};
+
+pub struct PostgresIdempotencyKeyRepo {
+    write_pool: PgPool,
+}
+
+impl PostgresIdempotencyKeyRepo {
+    /// Reads go to the primary too: a key claimed a moment ago must not look
+    /// free because a replica hasn't caught up.
+    pub fn new(write_pool: PgPool) -> Self {
+        Self { write_pool }
+    }
+
+    fn parse_record(row: &PgRow) -> DbResult<IdempotencyRecord> {
+        let response =
+            row.get::<Option<i32>, _>("response_status")
+                .map(|status| IdempotentResponse {
+                    status: status as u16,
+                    content_type: row.get("response_content_type"),
+                    body: row
+                        .get::<Option<Vec<u8>>, _>("response_body")
+                        .unwrap_or_default(),
+                });
+        Ok(IdempotencyRecord {
+            key: row.get("key_hash"),
+            request_hash: row.get("request_hash"),
+            status: row
+                .get::<String, _>("status")
+                .parse()
+                .map_err(DbError::Internal)?,
+            response,
+            created_at: row.get("created_at"),
+            expires_at: row.get("expires_at"),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl IdempotencyKeyRepo for PostgresIdempotencyKeyRepo {
+    async fn claim(&self, record: &IdempotencyRecord) -> DbResult<Option<IdempotencyRecord>> {
+        // The holder can release its claim between our insert and select;
+        // a second attempt then claims the key.
+        for _ in 0..2 {
+            let claimed = sqlx::query(
+                r#"
+                INSERT INTO idempotency_keys (
+                    key_hash, request_hash, status, created_at, expires_at
+                )
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (key_hash) DO UPDATE SET
+                    request_hash = EXCLUDED.request_hash,
+                    status = EXCLUDED.status,
+                    response_status = NULL,
+                    response_content_type = NULL,
+                    response_body = NULL,
+                    created_at = EXCLUDED.created_at,
+                    expires_at = EXCLUDED.expires_at
+                WHERE idempotency_keys.expires_at <= EXCLUDED.created_at
+                "#,
+            )
+            .bind(&record.key)
+            .bind(&record.request_hash)
+            .bind(record.status.as_str())
+            .bind(truncate_to_millis(record.created_at))
+            .bind(truncate_to_millis(record.expires_at))
+            .execute(&self.write_pool)
+            .await?
+            .rows_affected()
+                > 0;
+            if claimed {
+                return Ok(None);
+            }
+
+            let existing = sqlx::query(
+                r#"
+                SELECT key_hash, request_hash, status, response_status,
+                       response_content_type, response_body, created_at, expires_at
+                FROM idempotency_keys
+                WHERE key_hash = $1
+                "#,
+            )
+            .bind(&record.key)
+            .fetch_optional(&self.write_pool)
+            .await?;
+            if let Some(row) = existing {
+                return Self::parse_record(&row).map(Some);
+            }
+        }
+
+        Err(DbError::Conflict(
+            "Idempotency key was claimed and released concurrently".to_string(),
+        ))
+    }
+
+    async fn complete(&self, key: &str, response: Option<&IdempotentResponse>) -> DbResult<()> {
+        sqlx::query(
+            r#"
+            UPDATE idempotency_keys
+            SET status = $1, response_status = $2, response_content_type = $3, response_body = $4
+            WHERE key_hash = $5
+            "#,
+        )
+        .bind(IdempotencyStatus::Completed.as_str())
+        .bind(response.map(|r| i32::from(r.status)))
+        .bind(response.and_then(|r| r.content_type.as_deref()))
+        .bind(response.map(|r| r.body.as_slice()))
+        .bind(key)
+        .execute(&self.write_pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn release(&self, key: &str) -> DbResult<()> {
+        sqlx::query("DELETE FROM idempotency_keys WHERE key_hash = $1 AND status = $2")
+            .bind(key)
+            .bind(IdempotencyStatus::InProgress.as_str())
+            .execute(&self.write_pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_expired(&self, now: DateTime<Utc>) -> DbResult<u64> {
+        let result = sqlx::query("DELETE FROM idempotency_keys WHERE expires_at <= $1")
+            .bind(truncate_to_millis(now))
+            .execute(&self.write_pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,292 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Row, postgres::PgRow};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{
+            CursorDirection, InvitationRepo, ListParams, ListResult, PageCursors, cursor_from_row,
+            truncate_to_millis,
+        },
+    },
+    models::{Invitation, InvitationStatus, NewInvitation},
+};
+
+const COLUMNS: &str = "id, org_id, team_id, email, role, status, invited_by, expires_at, \
+                       send_count, last_sent_at, accepted_at, accepted_by, revoked_at, \
+                       created_at, updated_at";
+
+pub struct PostgresInvitationRepo {
+    write_pool: PgPool,
+    read_pool: PgPool,
+}
+
+impl PostgresInvitationRepo {
+    pub fn new(write_pool: PgPool, read_pool: Option<PgPool>) -> Self {
+        let read_pool = read_pool.unwrap_or_else(|| write_pool.clone());
+        Self {
+            write_pool,
+            read_pool,
+        }
+    }
+
+    fn parse_invitation(row: &PgRow) -> DbResult<Invitation> {
+        let expires_at: DateTime<Utc> = row.get("expires_at");
+
+        Ok(Invitation {
+            id: row.get("id"),
+            org_id: row.get("org_id"),
+            team_id: row.get("team_id"),
+            email: row.get("email"),
+            role: row.get("role"),
+            status: InvitationStatus::from_stored(
+                &row.get::<String, _>("status"),
+                expires_at,
+                Utc::now(),
+            )
+            .map_err(DbError::Internal)?,
+            invited_by: row.get("invited_by"),
+            expires_at,
+            send_count: row.get("send_count"),
+            last_sent_at: row.get("last_sent_at"),
+            accepted_at: row.get("accepted_at"),
+            accepted_by: row.get("accepted_by"),
+            revoked_at: row.get("revoked_at"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl InvitationRepo for PostgresInvitationRepo {
+    async fn create(&self, input: NewInvitation) -> DbResult<Invitation> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+        let expires_at = truncate_to_millis(input.expires_at);
+
+        sqlx::query(
+            r#"
+            INSERT INTO invitations (
+                id, org_id, team_id, email, role, token_hash, status, invited_by,
+                expires_at, send_count, created_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, 'pending', $7, $8, 0, $9, $9)
+            "#,
+        )
+        .bind(id)
+        .bind(input.org_id)
+        .bind(input.team_id)
+        .bind(&input.email)
+        .bind(&input.role)
+        .bind(&input.token_hash)
+        .bind(input.invited_by)
+        .bind(expires_at)
+        .bind(now)
+        .execute(&self.write_pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+                DbError::Conflict("Invitation token already exists".to_string())
+            }
+            _ => DbError::from(e),
+        })?;
+
+        Ok(Invitation {
+            id,
+            org_id: input.org_id,
+            team_id: input.team_id,
+            email: input.email,
+            role: input.role,
+            status: InvitationStatus::Pending,
+            invited_by: input.invited_by,
+            expires_at,
+            send_count: 0,
+            last_sent_at: None,
+            accepted_at: None,
+            accepted_by: None,
+            revoked_at: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<Invitation>> {
+        let sql = format!("SELECT {COLUMNS} FROM invitations WHERE id = $1");
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .fetch_optional(&self.read_pool)
+            .await?;
+
+        row.map(|r| Self::parse_invitation(&r)).transpose()
+    }
+
+    async fn get_by_token_hash(&self, token_hash: &str) -> DbResult<Option<Invitation>> {
+        // Read from the primary: the token may have just been rotated
+        let sql = format!("SELECT {COLUMNS} FROM invitations WHERE token_hash = $1");
+        let row = sqlx::query(&sql)
+            .bind(token_hash)
+            .fetch_optional(&self.write_pool)
+            .await?;
+
+        row.map(|r| Self::parse_invitation(&r)).transpose()
+    }
+
+    async fn list_by_org(
+        &self,
+        org_id: Uuid,
+        params: ListParams,
+    ) -> DbResult<ListResult<Invitation>> {
+        let limit = params.limit.unwrap_or(100);
+        let fetch_limit = limit + 1;
+
+        let (comparison, order, should_reverse) =
+            params.sort_order.cursor_query_params(params.direction);
+        let (cursor_clause, limit_idx, order, should_reverse) = if params.cursor.is_some() {
+            (
+                format!("AND ROW(created_at, id) {} ROW($2, $3)", comparison),
+                4,
+                order,
+                should_reverse,
+            )
+        } else {
+            (String::new(), 2, params.sort_order.as_sql(), false)
+        };
+
+        let sql = format!(
+            r#"
+            SELECT {COLUMNS}
+            FROM invitations
+            WHERE org_id = $1 {cursor_clause}
+            ORDER BY created_at {order}, id {order}
+            LIMIT ${limit_idx}
+            "#
+        );
+
+        let mut q = sqlx::query(&sql).bind(org_id);
+        if let Some(ref cursor) = params.cursor {
+            q = q.bind(cursor.created_at).bind(cursor.id);
+        }
+        let rows = q.bind(fetch_limit).fetch_all(&self.read_pool).await?;
+
+        let has_more = rows.len() as i64 > limit;
+        let mut items = rows
+            .iter()
+            .take(limit as usize)
+            .map(Self::parse_invitation)
+            .collect::<DbResult<Vec<_>>>()?;
+
+        if should_reverse {
+            items.reverse();
+        }
+
+        let direction = if params.cursor.is_some() {
+            params.direction
+        } else {
+            CursorDirection::Forward
+        };
+        let cursors =
+            PageCursors::from_items(&items, has_more, direction, params.cursor.as_ref(), |i| {
+                cursor_from_row(i.created_at, i.id)
+            });
+
+        Ok(ListResult::new(items, has_more, cursors))
+    }
+
+    async fn list_pending_by_email(
+        &self,
+        email: &str,
+        now: DateTime<Utc>,
+    ) -> DbResult<Vec<Invitation>> {
+        let sql = format!(
+            "SELECT {COLUMNS} FROM invitations \
+             WHERE email = $1 AND status = 'pending' AND expires_at > $2 \
+             ORDER BY created_at ASC, id ASC"
+        );
+        let rows = sqlx::query(&sql)
+            .bind(email)
+            .bind(now)
+            .fetch_all(&self.write_pool)
+            .await?;
+
+        rows.iter().map(Self::parse_invitation).collect()
+    }
+
+    async fn refresh_token(
+        &self,
+        id: Uuid,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> DbResult<Invitation> {
+        let sql = format!(
+            "UPDATE invitations SET token_hash = $1, expires_at = $2, updated_at = $3 \
+             WHERE id = $4 AND status = 'pending' \
+             RETURNING {COLUMNS}"
+        );
+        let row = sqlx::query(&sql)
+            .bind(token_hash)
+            .bind(truncate_to_millis(expires_at))
+            .bind(truncate_to_millis(Utc::now()))
+            .bind(id)
+            .fetch_optional(&self.write_pool)
+            .await?
+            .ok_or(DbError::NotFound)?;
+
+        Self::parse_invitation(&row)
+    }
+
+    async fn mark_sent(&self, id: Uuid) -> DbResult<()> {
+        let now = truncate_to_millis(Utc::now());
+        let result = sqlx::query(
+            r#"
+            UPDATE invitations SET send_count = send_count + 1, last_sent_at = $1, updated_at = $1
+            WHERE id = $2
+            "#,
+        )
+        .bind(now)
+        .bind(id)
+        .execute(&self.write_pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+        Ok(())
+    }
+
+    async fn revoke(&self, id: Uuid) -> DbResult<Invitation> {
+        let sql = format!(
+            "UPDATE invitations SET status = 'revoked', revoked_at = $1, updated_at = $1 \
+             WHERE id = $2 AND status = 'pending' \
+             RETURNING {COLUMNS}"
+        );
+        let row = sqlx::query(&sql)
+            .bind(truncate_to_millis(Utc::now()))
+            .bind(id)
+            .fetch_optional(&self.write_pool)
+            .await?
+            .ok_or(DbError::NotFound)?;
+
+        Self::parse_invitation(&row)
+    }
+
+    async fn mark_accepted(&self, id: Uuid, user_id: Uuid) -> DbResult<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE invitations SET status = 'accepted', accepted_at = $1, accepted_by = $2,
+                updated_at = $1
+            WHERE id = $3 AND status = 'pending'
+            "#,
+        )
+        .bind(truncate_to_millis(Utc::now()))
+        .bind(user_id)
+        .bind(id)
+        .execute(&self.write_pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,73 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Row};
+
+use crate::{
+    db::{
+        error::DbResult,
+        repos::{JobLeaderRepo, truncate_to_millis},
+    },
+    models::JobLeader,
+};
+
+pub struct PostgresJobLeaderRepo {
+    write_pool: PgPool,
+    read_pool: PgPool,
+}
+
+impl PostgresJobLeaderRepo {
+    pub fn new(write_pool: PgPool, read_pool: Option<PgPool>) -> Self {
+        let read_pool = read_pool.unwrap_or_else(|| write_pool.clone());
+        Self {
+            write_pool,
+            read_pool,
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl JobLeaderRepo for PostgresJobLeaderRepo {
+    async fn record_run(&self, job: &str, node_id: &str, now: DateTime<Utc>) -> DbResult<()> {
+        let now = truncate_to_millis(now);
+
+        sqlx::query(
+            r#"
+            INSERT INTO job_leaders (job, node_id, acquired_at, last_run_at)
+            VALUES ($1, $2, $3, $3)
+            ON CONFLICT (job) DO UPDATE SET
+                acquired_at = CASE
+                    WHEN job_leaders.node_id = EXCLUDED.node_id THEN job_leaders.acquired_at
+                    ELSE EXCLUDED.acquired_at
+                END,
+                node_id = EXCLUDED.node_id,
+                last_run_at = EXCLUDED.last_run_at
+            "#,
+        )
+        .bind(job)
+        .bind(node_id)
+        .bind(now)
+        .execute(&self.write_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn list(&self) -> DbResult<Vec<JobLeader>> {
+        let rows = sqlx::query(
+            "SELECT job, node_id, acquired_at, last_run_at FROM job_leaders ORDER BY job ASC",
+        )
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| JobLeader {
+                job: row.get("job"),
+                node_id: row.get("node_id"),
+                acquired_at: row.get("acquired_at"),
+                last_run_at: row.get("last_run_at"),
+            })
+            .collect())
+    }
+}
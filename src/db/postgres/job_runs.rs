@@ -0,0 +1,169 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Row, postgres::PgRow};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{JobRunRepo, truncate_to_millis},
+    },
+    models::{CreateJobRun, JobRun},
+};
+
+const COLUMNS: &str = "id, job, node_id, triggered_by, status, summary, error, started_at, \
+                       finished_at, duration_ms";
+
+pub struct PostgresJobRunRepo {
+    write_pool: PgPool,
+    read_pool: PgPool,
+}
+
+impl PostgresJobRunRepo {
+    pub fn new(write_pool: PgPool, read_pool: Option<PgPool>) -> Self {
+        let read_pool = read_pool.unwrap_or_else(|| write_pool.clone());
+        Self {
+            write_pool,
+            read_pool,
+        }
+    }
+
+    fn parse_run(row: &PgRow) -> DbResult<JobRun> {
+        Ok(JobRun {
+            id: row.get("id"),
+            job: row.get("job"),
+            node_id: row.get("node_id"),
+            triggered_by: row
+                .get::<String, _>("triggered_by")
+                .parse()
+                .map_err(DbError::Internal)?,
+            status: row
+                .get::<String, _>("status")
+                .parse()
+                .map_err(DbError::Internal)?,
+            summary: row.get("summary"),
+            error: row.get("error"),
+            started_at: row.get("started_at"),
+            finished_at: row.get("finished_at"),
+            duration_ms: row.get("duration_ms"),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl JobRunRepo for PostgresJobRunRepo {
+    async fn create(&self, input: CreateJobRun) -> DbResult<JobRun> {
+        let id = Uuid::new_v4();
+        let started_at = truncate_to_millis(input.started_at);
+        let finished_at = truncate_to_millis(input.finished_at);
+        let duration_ms = (finished_at - started_at).num_milliseconds().max(0);
+
+        sqlx::query(
+            r#"
+            INSERT INTO job_runs (
+                id, job, node_id, triggered_by, status, summary, error,
+                started_at, finished_at, duration_ms
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            "#,
+        )
+        .bind(id)
+        .bind(&input.job)
+        .bind(&input.node_id)
+        .bind(input.triggered_by.as_str())
+        .bind(input.status.as_str())
+        .bind(&input.summary)
+        .bind(&input.error)
+        .bind(started_at)
+        .bind(finished_at)
+        .bind(duration_ms)
+        .execute(&self.write_pool)
+        .await?;
+
+        Ok(JobRun {
+            id,
+            job: input.job,
+            node_id: input.node_id,
+            triggered_by: input.triggered_by,
+            status: input.status,
+            summary: input.summary,
+            error: input.error,
+            started_at,
+            finished_at,
+            duration_ms,
+        })
+    }
+
+    async fn list(&self, job: &str, limit: i64) -> DbResult<Vec<JobRun>> {
+        let sql = format!(
+            "SELECT {COLUMNS} FROM job_runs WHERE job = $1 ORDER BY started_at DESC, id DESC LIMIT $2"
+        );
+        let rows = sqlx::query(&sql)
+            .bind(job)
+            .bind(limit)
+            .fetch_all(&self.read_pool)
+            .await?;
+
+        rows.iter().map(Self::parse_run).collect()
+    }
+
+    async fn latest(&self) -> DbResult<Vec<JobRun>> {
+        let sql = format!(
+            "SELECT DISTINCT ON (job) {COLUMNS} FROM job_runs \
+             ORDER BY job ASC, started_at DESC, id DESC"
+        );
+        let rows = sqlx::query(&sql).fetch_all(&self.read_pool).await?;
+
+        rows.iter().map(Self::parse_run).collect()
+    }
+
+    async fn prune(&self, job: &str, keep: i64) -> DbResult<u64> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM job_runs
+            WHERE job = $1 AND id NOT IN (
+                SELECT id FROM job_runs
+                WHERE job = $1
+                ORDER BY started_at DESC, id DESC
+                LIMIT $2
+            )
+            "#,
+        )
+        .bind(job)
+        .bind(keep)
+        .execute(&self.write_pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn pause(&self, job: &str, now: DateTime<Utc>) -> DbResult<()> {
+        sqlx::query(
+            "INSERT INTO paused_jobs (job, paused_at) VALUES ($1, $2) ON CONFLICT (job) DO NOTHING",
+        )
+        .bind(job)
+        .bind(truncate_to_millis(now))
+        .execute(&self.write_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn resume(&self, job: &str) -> DbResult<bool> {
+        let result = sqlx::query("DELETE FROM paused_jobs WHERE job = $1")
+            .bind(job)
+            .execute(&self.write_pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list_paused(&self) -> DbResult<Vec<String>> {
+        let rows = sqlx::query("SELECT job FROM paused_jobs ORDER BY job ASC")
+            .fetch_all(&self.read_pool)
+            .await?;
+
+        Ok(rows.iter().map(|row| row.get("job")).collect())
+    }
+}
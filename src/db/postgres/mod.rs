@@ -1,22 +1,43 @@
+mod admin_passkeys;
+mod admin_totp;
+mod agents;
 mod api_keys;
 mod audit_logs;
+mod client_cert_mappings;
 mod containers;
 mod conversations;
 #[cfg(feature = "sso")]
 mod domain_verifications;
+mod email_log;
+mod federation;
 mod files;
+mod fine_tuning_jobs;
+mod http_tools;
+mod idempotency_keys;
+mod invitations;
+mod job_leaders;
+mod job_runs;
 #[cfg(feature = "mcp")]
 mod mcp_pending_approvals;
+mod model_catalog_entries;
 mod model_pricing;
 mod oauth_authorization_codes;
 mod org_rbac_policies;
+mod org_request_policies;
 #[cfg(feature = "sso")]
 mod org_sso_configs;
 mod organizations;
 mod projects;
+mod provider_files;
+mod provider_probes;
+mod provider_settings;
+mod provider_usage_imports;
 mod providers;
 mod response_events;
 mod responses;
+mod role_elevations;
+mod scheduled_reports;
+mod scheduled_tasks;
 #[cfg(feature = "sso")]
 mod scim_configs;
 #[cfg(feature = "sso")]
@@ -24,34 +45,58 @@ mod scim_group_mappings;
 #[cfg(feature = "sso")]
 mod scim_user_mappings;
 mod service_accounts;
+mod shadow_results;
 mod skills;
 #[cfg(feature = "sso")]
 mod sso_group_mappings;
 mod teams;
 mod templates;
 mod usage;
+mod usage_anomalies;
 mod users;
+mod vector_store_syncs;
 mod vector_stores;
 
+pub use admin_passkeys::PostgresAdminPasskeyRepo;
+pub use admin_totp::PostgresAdminTotpRepo;
+pub use agents::PostgresAgentRepo;
 pub use api_keys::PostgresApiKeyRepo;
 pub use audit_logs::PostgresAuditLogRepo;
+pub use client_cert_mappings::PostgresClientCertMappingRepo;
 pub use containers::PostgresContainersRepo;
 pub use conversations::PostgresConversationRepo;
 #[cfg(feature = "sso")]
 pub use domain_verifications::PostgresDomainVerificationRepo;
+pub use email_log::PostgresEmailLogRepo;
+pub use federation::PostgresFederationRepo;
 pub use files::PostgresFilesRepo;
+pub use fine_tuning_jobs::PostgresFineTuningJobRepo;
+pub use http_tools::PostgresHttpToolRepo;
+pub use idempotency_keys::PostgresIdempotencyKeyRepo;
+pub use invitations::PostgresInvitationRepo;
+pub use job_leaders::PostgresJobLeaderRepo;
+pub use job_runs::PostgresJobRunRepo;
 #[cfg(feature = "mcp")]
 pub use mcp_pending_approvals::PostgresMcpPendingApprovalsRepo;
+pub use model_catalog_entries::PostgresModelCatalogEntryRepo;
 pub use model_pricing::PostgresModelPricingRepo;
 pub use oauth_authorization_codes::PostgresOAuthAuthorizationCodeRepo;
 pub use org_rbac_policies::PostgresOrgRbacPolicyRepo;
+pub use org_request_policies::PostgresOrgRequestPolicyRepo;
 #[cfg(feature = "sso")]
 pub use org_sso_configs::PostgresOrgSsoConfigRepo;
 pub use organizations::PostgresOrganizationRepo;
 pub use projects::PostgresProjectRepo;
+pub use provider_files::PostgresProviderFileRepo;
+pub use provider_probes::PostgresProviderProbeRepo;
+pub use provider_settings::PostgresProviderSettingsRepo;
+pub use provider_usage_imports::PostgresProviderUsageImportRepo;
 pub use providers::PostgresDynamicProviderRepo;
 pub use response_events::PostgresResponseEventsRepo;
 pub use responses::PostgresResponsesRepo;
+pub use role_elevations::PostgresRoleElevationRepo;
+pub use scheduled_reports::PostgresReportRunRepo;
+pub use scheduled_tasks::PostgresScheduledTaskRepo;
 #[cfg(feature = "sso")]
 pub use scim_configs::PostgresOrgScimConfigRepo;
 #[cfg(feature = "sso")]
@@ -59,11 +104,14 @@ pub use scim_group_mappings::PostgresScimGroupMappingRepo;
 #[cfg(feature = "sso")]
 pub use scim_user_mappings::PostgresScimUserMappingRepo;
 pub use service_accounts::PostgresServiceAccountRepo;
+pub use shadow_results::PostgresShadowResultRepo;
 pub use skills::PostgresSkillRepo;
 #[cfg(feature = "sso")]
 pub use sso_group_mappings::PostgresSsoGroupMappingRepo;
 pub use teams::PostgresTeamRepo;
 pub use templates::PostgresTemplateRepo;
 pub use usage::PostgresUsageRepo;
+pub use usage_anomalies::PostgresUsageAnomalyRepo;
 pub use users::PostgresUserRepo;
+pub use vector_store_syncs::PostgresVectorStoreSyncRepo;
 pub use vector_stores::PostgresVectorStoresRepo;
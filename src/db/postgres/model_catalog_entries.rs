@@ -0,0 +1,137 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::{PgPool, Row, postgres::PgRow};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{ModelCatalogEntryRepo, truncate_to_millis},
+    },
+    models::{CatalogEntryMetadata, CreateModelCatalogEntry, ModelCatalogEntry},
+};
+
+const COLUMNS: &str = "id, provider, model, metadata, created_at, updated_at";
+
+pub struct PostgresModelCatalogEntryRepo {
+    write_pool: PgPool,
+    read_pool: PgPool,
+}
+
+impl PostgresModelCatalogEntryRepo {
+    pub fn new(write_pool: PgPool, read_pool: Option<PgPool>) -> Self {
+        let read_pool = read_pool.unwrap_or_else(|| write_pool.clone());
+        Self {
+            write_pool,
+            read_pool,
+        }
+    }
+
+    fn serialize_metadata(metadata: &CatalogEntryMetadata) -> DbResult<serde_json::Value> {
+        serde_json::to_value(metadata)
+            .map_err(|e| DbError::Internal(format!("failed to serialize metadata: {e}")))
+    }
+
+    fn parse_entry(row: &PgRow) -> DbResult<ModelCatalogEntry> {
+        let metadata = serde_json::from_value(row.get("metadata"))
+            .map_err(|e| DbError::Internal(format!("failed to deserialize metadata: {e}")))?;
+        Ok(ModelCatalogEntry {
+            id: row.get("id"),
+            provider: row.get("provider"),
+            model: row.get("model"),
+            metadata,
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl ModelCatalogEntryRepo for PostgresModelCatalogEntryRepo {
+    async fn create(&self, input: CreateModelCatalogEntry) -> DbResult<ModelCatalogEntry> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+
+        sqlx::query(
+            r#"
+            INSERT INTO model_catalog_entries (id, provider, model, metadata, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(id)
+        .bind(&input.provider)
+        .bind(&input.model)
+        .bind(Self::serialize_metadata(&input.metadata)?)
+        .bind(now)
+        .bind(now)
+        .execute(&self.write_pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+                DbError::Conflict(format!(
+                    "Catalog entry for '{}/{}' already exists",
+                    input.provider, input.model
+                ))
+            }
+            _ => DbError::from(e),
+        })?;
+
+        Ok(ModelCatalogEntry {
+            id,
+            provider: input.provider,
+            model: input.model,
+            metadata: input.metadata,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<ModelCatalogEntry>> {
+        let sql = format!("SELECT {COLUMNS} FROM model_catalog_entries WHERE id = $1");
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .fetch_optional(&self.read_pool)
+            .await?;
+
+        row.map(|r| Self::parse_entry(&r)).transpose()
+    }
+
+    async fn list(&self) -> DbResult<Vec<ModelCatalogEntry>> {
+        let sql =
+            format!("SELECT {COLUMNS} FROM model_catalog_entries ORDER BY provider ASC, model ASC");
+        let rows = sqlx::query(&sql).fetch_all(&self.read_pool).await?;
+
+        rows.iter().map(Self::parse_entry).collect()
+    }
+
+    async fn update(
+        &self,
+        id: Uuid,
+        metadata: CatalogEntryMetadata,
+    ) -> DbResult<ModelCatalogEntry> {
+        let now = truncate_to_millis(Utc::now());
+        let sql = format!(
+            "UPDATE model_catalog_entries SET metadata = $1, updated_at = $2 WHERE id = $3 \
+             RETURNING {COLUMNS}"
+        );
+        let row = sqlx::query(&sql)
+            .bind(Self::serialize_metadata(&metadata)?)
+            .bind(now)
+            .bind(id)
+            .fetch_optional(&self.write_pool)
+            .await?
+            .ok_or(DbError::NotFound)?;
+
+        Self::parse_entry(&row)
+    }
+
+    async fn delete(&self, id: Uuid) -> DbResult<bool> {
+        let result = sqlx::query("DELETE FROM model_catalog_entries WHERE id = $1")
+            .bind(id)
+            .execute(&self.write_pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
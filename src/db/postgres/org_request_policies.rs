@@ -0,0 +1,236 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::{PgPool, Row, postgres::PgRow};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{OrgRequestPolicyRepo, truncate_to_millis},
+    },
+    models::{CreateOrgRequestPolicy, OrgRequestPolicy, UpdateOrgRequestPolicy},
+};
+
+const COLUMNS: &str = "id, org_id, name, description, condition, action::TEXT AS action, \
+                       modifications, message, priority, enabled, created_at, updated_at";
+
+pub struct PostgresOrgRequestPolicyRepo {
+    write_pool: PgPool,
+    read_pool: PgPool,
+}
+
+impl PostgresOrgRequestPolicyRepo {
+    pub fn new(write_pool: PgPool, read_pool: Option<PgPool>) -> Self {
+        let read_pool = read_pool.unwrap_or_else(|| write_pool.clone());
+        Self {
+            write_pool,
+            read_pool,
+        }
+    }
+
+    fn parse_policy(row: &PgRow) -> DbResult<OrgRequestPolicy> {
+        Ok(OrgRequestPolicy {
+            id: row.get("id"),
+            org_id: row.get("org_id"),
+            name: row.get("name"),
+            description: row.get("description"),
+            condition: row.get("condition"),
+            action: row
+                .get::<String, _>("action")
+                .parse()
+                .map_err(DbError::Internal)?,
+            modifications: row.get::<Option<serde_json::Value>, _>("modifications"),
+            message: row.get("message"),
+            priority: row.get("priority"),
+            enabled: row.get("enabled"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        })
+    }
+
+    async fn list(&self, org_id: Uuid, enabled_only: bool) -> DbResult<Vec<OrgRequestPolicy>> {
+        let filter = if enabled_only {
+            "AND enabled = TRUE"
+        } else {
+            ""
+        };
+        let sql = format!(
+            "SELECT {COLUMNS} FROM org_request_policies WHERE org_id = $1 {filter} \
+             ORDER BY priority DESC, name ASC"
+        );
+        let rows = sqlx::query(&sql)
+            .bind(org_id)
+            .fetch_all(&self.read_pool)
+            .await?;
+
+        rows.iter().map(Self::parse_policy).collect()
+    }
+}
+
+fn map_name_conflict(name: &str) -> impl FnOnce(sqlx::Error) -> DbError + '_ {
+    move |e| match e {
+        sqlx::Error::Database(db_err) if db_err.is_unique_violation() => DbError::Conflict(
+            format!("A request policy named '{name}' already exists in this organization"),
+        ),
+        _ => DbError::from(e),
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl OrgRequestPolicyRepo for PostgresOrgRequestPolicyRepo {
+    async fn create(
+        &self,
+        org_id: Uuid,
+        input: CreateOrgRequestPolicy,
+    ) -> DbResult<OrgRequestPolicy> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+
+        sqlx::query(
+            r#"
+            INSERT INTO org_request_policies (
+                id, org_id, name, description, condition, action, modifications, message,
+                priority, enabled, created_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6::request_policy_action, $7, $8, $9, $10, $11, $11)
+            "#,
+        )
+        .bind(id)
+        .bind(org_id)
+        .bind(&input.name)
+        .bind(&input.description)
+        .bind(&input.condition)
+        .bind(input.action.as_str())
+        .bind(&input.modifications)
+        .bind(&input.message)
+        .bind(input.priority)
+        .bind(input.enabled)
+        .bind(now)
+        .execute(&self.write_pool)
+        .await
+        .map_err(map_name_conflict(&input.name))?;
+
+        Ok(OrgRequestPolicy {
+            id,
+            org_id,
+            name: input.name,
+            description: input.description,
+            condition: input.condition,
+            action: input.action,
+            modifications: input.modifications,
+            message: input.message,
+            priority: input.priority,
+            enabled: input.enabled,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<OrgRequestPolicy>> {
+        let sql = format!("SELECT {COLUMNS} FROM org_request_policies WHERE id = $1");
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .fetch_optional(&self.read_pool)
+            .await?;
+
+        row.map(|r| Self::parse_policy(&r)).transpose()
+    }
+
+    async fn list_by_org(&self, org_id: Uuid) -> DbResult<Vec<OrgRequestPolicy>> {
+        self.list(org_id, false).await
+    }
+
+    async fn list_enabled_by_org(&self, org_id: Uuid) -> DbResult<Vec<OrgRequestPolicy>> {
+        self.list(org_id, true).await
+    }
+
+    async fn update(&self, id: Uuid, input: UpdateOrgRequestPolicy) -> DbResult<OrgRequestPolicy> {
+        // Read from the primary so the update doesn't race replica lag.
+        let sql = format!("SELECT {COLUMNS} FROM org_request_policies WHERE id = $1");
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .fetch_optional(&self.write_pool)
+            .await?
+            .ok_or(DbError::NotFound)?;
+        let mut policy = Self::parse_policy(&row)?;
+
+        if let Some(name) = input.name {
+            policy.name = name;
+        }
+        if let Some(description) = input.description {
+            policy.description = description;
+        }
+        if let Some(condition) = input.condition {
+            policy.condition = condition;
+        }
+        if let Some(action) = input.action {
+            policy.action = action;
+        }
+        if let Some(modifications) = input.modifications {
+            policy.modifications = modifications;
+        }
+        if let Some(message) = input.message {
+            policy.message = message;
+        }
+        if let Some(priority) = input.priority {
+            policy.priority = priority;
+        }
+        if let Some(enabled) = input.enabled {
+            policy.enabled = enabled;
+        }
+        policy.updated_at = truncate_to_millis(Utc::now());
+
+        let result = sqlx::query(
+            r#"
+            UPDATE org_request_policies
+            SET name = $1, description = $2, condition = $3,
+                action = $4::request_policy_action, modifications = $5, message = $6,
+                priority = $7, enabled = $8, updated_at = $9
+            WHERE id = $10
+            "#,
+        )
+        .bind(&policy.name)
+        .bind(&policy.description)
+        .bind(&policy.condition)
+        .bind(policy.action.as_str())
+        .bind(&policy.modifications)
+        .bind(&policy.message)
+        .bind(policy.priority)
+        .bind(policy.enabled)
+        .bind(policy.updated_at)
+        .bind(id)
+        .execute(&self.write_pool)
+        .await
+        .map_err(map_name_conflict(&policy.name))?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        Ok(policy)
+    }
+
+    async fn delete(&self, id: Uuid) -> DbResult<()> {
+        let result = sqlx::query("DELETE FROM org_request_policies WHERE id = $1")
+            .bind(id)
+            .execute(&self.write_pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        Ok(())
+    }
+
+    async fn count_by_org(&self, org_id: Uuid) -> DbResult<i64> {
+        let row =
+            sqlx::query("SELECT COUNT(*) as count FROM org_request_policies WHERE org_id = $1")
+                .bind(org_id)
+                .fetch_one(&self.read_pool)
+                .await?;
+
+        Ok(row.get::<i64, _>("count"))
+    }
+}
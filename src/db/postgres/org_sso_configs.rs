@@ -9,7 +9,7 @@ use crate::{
     },
     models::{
         CreateOrgSsoConfig, OrgSsoConfig, OrgSsoConfigWithSecret, SsoEnforcementMode,
-        SsoProviderType, UpdateOrgSsoConfig,
+        SsoProviderType, SsoRoleRule, UpdateOrgSsoConfig,
     },
 };
 
@@ -438,6 +438,45 @@ impl OrgSsoConfigRepo for PostgresOrgSsoConfigRepo {
         Ok(())
     }
 
+    async fn get_role_rules(&self, org_id: Uuid) -> DbResult<Vec<SsoRoleRule>> {
+        let row = sqlx::query("SELECT role_rules FROM org_sso_configs WHERE org_id = $1")
+            .bind(org_id)
+            .fetch_optional(&self.read_pool)
+            .await?;
+
+        let Some(json) = row.and_then(|r| r.get::<Option<serde_json::Value>, _>("role_rules"))
+        else {
+            return Ok(Vec::new());
+        };
+        serde_json::from_value(json)
+            .map_err(|e| DbError::Internal(format!("Invalid role_rules JSON: {e}")))
+    }
+
+    async fn set_role_rules(&self, org_id: Uuid, rules: &[SsoRoleRule]) -> DbResult<()> {
+        let json =
+            if rules.is_empty() {
+                None
+            } else {
+                Some(serde_json::to_value(rules).map_err(|e| {
+                    DbError::Internal(format!("Failed to serialize role rules: {e}"))
+                })?)
+            };
+
+        let result = sqlx::query(
+            "UPDATE org_sso_configs SET role_rules = $1, updated_at = NOW() WHERE org_id = $2",
+        )
+        .bind(json)
+        .bind(org_id)
+        .execute(&self.write_pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        Ok(())
+    }
+
     async fn find_enabled_oidc_by_issuer(&self, issuer: &str) -> DbResult<Vec<OrgSsoConfig>> {
         let rows = sqlx::query(
             r#"
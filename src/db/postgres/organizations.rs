@@ -1,5 +1,7 @@
+use std::collections::BTreeMap;
+
 use async_trait::async_trait;
-use sqlx::{PgPool, Row};
+use sqlx::{PgPool, Row, postgres::PgRow};
 use uuid::Uuid;
 
 use crate::{
@@ -10,9 +12,72 @@ use crate::{
             cursor_from_row,
         },
     },
-    models::{CreateOrganization, Organization, UpdateOrganization},
+    models::{
+        CreateOrganization, ModelAccessPolicy, ModelDegradationPolicy, OrgEntitlements,
+        OrgNetworkPolicy, Organization, ParameterGovernance, UpdateOrganization,
+    },
 };
 
+fn org_from_row(row: &PgRow) -> DbResult<Organization> {
+    Ok(Organization {
+        id: row.get("id"),
+        slug: row.get("slug"),
+        name: row.get("name"),
+        data_residency: row
+            .get::<Option<serde_json::Value>, _>("data_residency")
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| DbError::Internal(format!("failed to deserialize data_residency: {e}")))?,
+        request_defaults: row
+            .get::<Option<serde_json::Value>, _>("request_defaults")
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| {
+                DbError::Internal(format!("failed to deserialize request_defaults: {e}"))
+            })?,
+        conversation_summaries: row
+            .get::<Option<serde_json::Value>, _>("conversation_summaries")
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| {
+                DbError::Internal(format!("failed to deserialize conversation_summaries: {e}"))
+            })?,
+        network_policy: row
+            .get::<Option<serde_json::Value>, _>("network_policy")
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| DbError::Internal(format!("failed to deserialize network_policy: {e}")))?,
+        parameter_governance: row
+            .get::<Option<serde_json::Value>, _>("parameter_governance")
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| {
+                DbError::Internal(format!("failed to deserialize parameter_governance: {e}"))
+            })?,
+        model_access: row
+            .get::<Option<serde_json::Value>, _>("model_access")
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| DbError::Internal(format!("failed to deserialize model_access: {e}")))?,
+        model_degradation: row
+            .get::<Option<serde_json::Value>, _>("model_degradation")
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| {
+                DbError::Internal(format!("failed to deserialize model_degradation: {e}"))
+            })?,
+        entitlements: row
+            .get::<Option<serde_json::Value>, _>("entitlements")
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| DbError::Internal(format!("failed to deserialize entitlements: {e}")))?,
+        labels: serde_json::from_value(row.get("labels"))
+            .map_err(|e| DbError::Internal(format!("failed to deserialize labels: {e}")))?,
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    })
+}
+
 pub struct PostgresOrganizationRepo {
     write_pool: PgPool,
     read_pool: PgPool,
@@ -48,12 +113,13 @@ impl PostgresOrganizationRepo {
 
         let query = format!(
             r#"
-            SELECT id, slug, name, created_at, updated_at
+            SELECT id, slug, name, data_residency, request_defaults, conversation_summaries, network_policy, parameter_governance, model_access, model_degradation, entitlements, labels, created_at, updated_at
             FROM organizations
             WHERE ROW(created_at, id) {} ROW($1, $2)
             {}
+            AND labels @> $3
             ORDER BY created_at {}, id {}
-            LIMIT $3
+            LIMIT $4
             "#,
             comparison, deleted_filter, order, order
         );
@@ -61,6 +127,7 @@ impl PostgresOrganizationRepo {
         let rows = sqlx::query(&query)
             .bind(cursor.created_at)
             .bind(cursor.id)
+            .bind(params.labels.to_json())
             .bind(fetch_limit)
             .fetch_all(&self.read_pool)
             .await?;
@@ -69,14 +136,8 @@ impl PostgresOrganizationRepo {
         let mut items: Vec<Organization> = rows
             .into_iter()
             .take(limit as usize)
-            .map(|row| Organization {
-                id: row.get("id"),
-                slug: row.get("slug"),
-                name: row.get("name"),
-                created_at: row.get("created_at"),
-                updated_at: row.get("updated_at"),
-            })
-            .collect();
+            .map(|row| org_from_row(&row))
+            .collect::<DbResult<Vec<_>>>()?;
 
         if should_reverse {
             items.reverse();
@@ -101,7 +162,7 @@ impl OrganizationRepo for PostgresOrganizationRepo {
             r#"
             INSERT INTO organizations (id, slug, name)
             VALUES ($1, $2, $3)
-            RETURNING id, slug, name, created_at, updated_at
+            RETURNING id, slug, name, data_residency, request_defaults, conversation_summaries, network_policy, parameter_governance, model_access, model_degradation, entitlements, labels, created_at, updated_at
             "#,
         )
         .bind(id)
@@ -116,19 +177,13 @@ impl OrganizationRepo for PostgresOrganizationRepo {
             _ => DbError::from(e),
         })?;
 
-        Ok(Organization {
-            id: row.get("id"),
-            slug: row.get("slug"),
-            name: row.get("name"),
-            created_at: row.get("created_at"),
-            updated_at: row.get("updated_at"),
-        })
+        org_from_row(&row)
     }
 
     async fn get_by_id(&self, id: Uuid) -> DbResult<Option<Organization>> {
         let result = sqlx::query(
             r#"
-            SELECT id, slug, name, created_at, updated_at
+            SELECT id, slug, name, data_residency, request_defaults, conversation_summaries, network_policy, parameter_governance, model_access, model_degradation, entitlements, labels, created_at, updated_at
             FROM organizations
             WHERE id = $1 AND deleted_at IS NULL
             "#,
@@ -137,19 +192,13 @@ impl OrganizationRepo for PostgresOrganizationRepo {
         .fetch_optional(&self.read_pool)
         .await?;
 
-        Ok(result.map(|row| Organization {
-            id: row.get("id"),
-            slug: row.get("slug"),
-            name: row.get("name"),
-            created_at: row.get("created_at"),
-            updated_at: row.get("updated_at"),
-        }))
+        result.map(|row| org_from_row(&row)).transpose()
     }
 
     async fn get_by_slug(&self, slug: &str) -> DbResult<Option<Organization>> {
         let result = sqlx::query(
             r#"
-            SELECT id, slug, name, created_at, updated_at
+            SELECT id, slug, name, data_residency, request_defaults, conversation_summaries, network_policy, parameter_governance, model_access, model_degradation, entitlements, labels, created_at, updated_at
             FROM organizations
             WHERE slug = $1 AND deleted_at IS NULL
             "#,
@@ -162,13 +211,7 @@ impl OrganizationRepo for PostgresOrganizationRepo {
         .fetch_optional(&self.write_pool)
         .await?;
 
-        Ok(result.map(|row| Organization {
-            id: row.get("id"),
-            slug: row.get("slug"),
-            name: row.get("name"),
-            created_at: row.get("created_at"),
-            updated_at: row.get("updated_at"),
-        }))
+        result.map(|row| org_from_row(&row)).transpose()
     }
 
     async fn list(&self, params: ListParams) -> DbResult<ListResult<Organization>> {
@@ -186,22 +229,25 @@ impl OrganizationRepo for PostgresOrganizationRepo {
         // First page (no cursor provided)
         let query = if params.include_deleted {
             r#"
-            SELECT id, slug, name, created_at, updated_at
+            SELECT id, slug, name, data_residency, request_defaults, conversation_summaries, network_policy, parameter_governance, model_access, model_degradation, entitlements, labels, created_at, updated_at
             FROM organizations
+            WHERE labels @> $1
             ORDER BY created_at DESC, id DESC
-            LIMIT $1
+            LIMIT $2
             "#
         } else {
             r#"
-            SELECT id, slug, name, created_at, updated_at
+            SELECT id, slug, name, data_residency, request_defaults, conversation_summaries, network_policy, parameter_governance, model_access, model_degradation, entitlements, labels, created_at, updated_at
             FROM organizations
             WHERE deleted_at IS NULL
+            AND labels @> $1
             ORDER BY created_at DESC, id DESC
-            LIMIT $1
+            LIMIT $2
             "#
         };
 
         let rows = sqlx::query(query)
+            .bind(params.labels.to_json())
             .bind(fetch_limit)
             .fetch_all(&self.read_pool)
             .await?;
@@ -210,14 +256,8 @@ impl OrganizationRepo for PostgresOrganizationRepo {
         let items: Vec<Organization> = rows
             .into_iter()
             .take(limit as usize)
-            .map(|row| Organization {
-                id: row.get("id"),
-                slug: row.get("slug"),
-                name: row.get("name"),
-                created_at: row.get("created_at"),
-                updated_at: row.get("updated_at"),
-            })
-            .collect();
+            .map(|row| org_from_row(&row))
+            .collect::<DbResult<Vec<_>>>()?;
 
         // Generate cursors for pagination
         let cursors =
@@ -240,31 +280,236 @@ impl OrganizationRepo for PostgresOrganizationRepo {
     }
 
     async fn update(&self, id: Uuid, input: UpdateOrganization) -> DbResult<Organization> {
-        if let Some(name) = input.name {
-            let row = sqlx::query(
-                r#"
-                UPDATE organizations
-                SET name = $1, updated_at = NOW()
-                WHERE id = $2 AND deleted_at IS NULL
-                RETURNING id, slug, name, created_at, updated_at
-                "#,
-            )
-            .bind(&name)
+        let has_name_update = input.name.is_some();
+        let has_residency_update = input.data_residency.is_some();
+        let has_defaults_update = input.request_defaults.is_some();
+        let has_summaries_update = input.conversation_summaries.is_some();
+
+        if !has_name_update
+            && !has_residency_update
+            && !has_defaults_update
+            && !has_summaries_update
+        {
+            return self.get_by_id(id).await?.ok_or(DbError::NotFound);
+        }
+
+        // Build dynamic update query with parameterized SET clauses
+        let mut set_clauses: Vec<String> = vec!["updated_at = NOW()".to_string()];
+        let mut param_idx = 1;
+        if has_name_update {
+            set_clauses.push(format!("name = ${}", param_idx));
+            param_idx += 1;
+        }
+        if has_residency_update {
+            set_clauses.push(format!("data_residency = ${}", param_idx));
+            param_idx += 1;
+        }
+        if has_defaults_update {
+            set_clauses.push(format!("request_defaults = ${}", param_idx));
+            param_idx += 1;
+        }
+        if has_summaries_update {
+            set_clauses.push(format!("conversation_summaries = ${}", param_idx));
+            param_idx += 1;
+        }
+
+        let query = format!(
+            r#"
+            UPDATE organizations
+            SET {}
+            WHERE id = ${} AND deleted_at IS NULL
+            RETURNING id, slug, name, data_residency, request_defaults, conversation_summaries, network_policy, parameter_governance, model_access, model_degradation, entitlements, labels, created_at, updated_at
+            "#,
+            set_clauses.join(", "),
+            param_idx
+        );
+
+        let mut query_builder = sqlx::query(&query);
+
+        if let Some(ref name) = input.name {
+            query_builder = query_builder.bind(name);
+        }
+        if let Some(ref policy) = input.data_residency {
+            query_builder =
+                query_builder.bind(policy.as_ref().and_then(|p| serde_json::to_value(p).ok()));
+        }
+        if let Some(ref defaults) = input.request_defaults {
+            query_builder =
+                query_builder.bind(defaults.as_ref().and_then(|d| serde_json::to_value(d).ok()));
+        }
+        if let Some(ref settings) = input.conversation_summaries {
+            query_builder =
+                query_builder.bind(settings.as_ref().and_then(|s| serde_json::to_value(s).ok()));
+        }
+
+        let row = query_builder
             .bind(id)
             .fetch_optional(&self.write_pool)
             .await?
             .ok_or(DbError::NotFound)?;
 
-            Ok(Organization {
-                id: row.get("id"),
-                slug: row.get("slug"),
-                name: row.get("name"),
-                created_at: row.get("created_at"),
-                updated_at: row.get("updated_at"),
-            })
-        } else {
-            self.get_by_id(id).await?.ok_or(DbError::NotFound)
-        }
+        org_from_row(&row)
+    }
+
+    async fn set_network_policy(
+        &self,
+        id: Uuid,
+        policy: Option<&OrgNetworkPolicy>,
+    ) -> DbResult<Organization> {
+        let policy = policy
+            .map(serde_json::to_value)
+            .transpose()
+            .map_err(|e| DbError::Internal(format!("failed to serialize network_policy: {e}")))?;
+
+        let row = sqlx::query(
+            r#"
+            UPDATE organizations
+            SET network_policy = $1, updated_at = NOW()
+            WHERE id = $2 AND deleted_at IS NULL
+            RETURNING id, slug, name, data_residency, request_defaults, conversation_summaries, network_policy, parameter_governance, model_access, model_degradation, entitlements, labels, created_at, updated_at
+            "#,
+        )
+        .bind(policy)
+        .bind(id)
+        .fetch_optional(&self.write_pool)
+        .await?
+        .ok_or(DbError::NotFound)?;
+
+        org_from_row(&row)
+    }
+
+    async fn set_parameter_governance(
+        &self,
+        id: Uuid,
+        governance: Option<&ParameterGovernance>,
+    ) -> DbResult<Organization> {
+        let governance = governance
+            .map(serde_json::to_value)
+            .transpose()
+            .map_err(|e| {
+                DbError::Internal(format!("failed to serialize parameter_governance: {e}"))
+            })?;
+
+        let row = sqlx::query(
+            r#"
+            UPDATE organizations
+            SET parameter_governance = $1, updated_at = NOW()
+            WHERE id = $2 AND deleted_at IS NULL
+            RETURNING id, slug, name, data_residency, request_defaults, conversation_summaries, network_policy, parameter_governance, model_access, model_degradation, entitlements, labels, created_at, updated_at
+            "#,
+        )
+        .bind(governance)
+        .bind(id)
+        .fetch_optional(&self.write_pool)
+        .await?
+        .ok_or(DbError::NotFound)?;
+
+        org_from_row(&row)
+    }
+
+    async fn set_model_access(
+        &self,
+        id: Uuid,
+        policy: Option<&ModelAccessPolicy>,
+    ) -> DbResult<Organization> {
+        let policy = policy
+            .map(serde_json::to_value)
+            .transpose()
+            .map_err(|e| DbError::Internal(format!("failed to serialize model_access: {e}")))?;
+
+        let row = sqlx::query(
+            r#"
+            UPDATE organizations
+            SET model_access = $1, updated_at = NOW()
+            WHERE id = $2 AND deleted_at IS NULL
+            RETURNING id, slug, name, data_residency, request_defaults, conversation_summaries, network_policy, parameter_governance, model_access, model_degradation, entitlements, labels, created_at, updated_at
+            "#,
+        )
+        .bind(policy)
+        .bind(id)
+        .fetch_optional(&self.write_pool)
+        .await?
+        .ok_or(DbError::NotFound)?;
+
+        org_from_row(&row)
+    }
+
+    async fn set_model_degradation(
+        &self,
+        id: Uuid,
+        policy: Option<&ModelDegradationPolicy>,
+    ) -> DbResult<Organization> {
+        let policy = policy.map(serde_json::to_value).transpose().map_err(|e| {
+            DbError::Internal(format!("failed to serialize model_degradation: {e}"))
+        })?;
+
+        let row = sqlx::query(
+            r#"
+            UPDATE organizations
+            SET model_degradation = $1, updated_at = NOW()
+            WHERE id = $2 AND deleted_at IS NULL
+            RETURNING id, slug, name, data_residency, request_defaults, conversation_summaries, network_policy, parameter_governance, model_access, model_degradation, entitlements, labels, created_at, updated_at
+            "#,
+        )
+        .bind(policy)
+        .bind(id)
+        .fetch_optional(&self.write_pool)
+        .await?
+        .ok_or(DbError::NotFound)?;
+
+        org_from_row(&row)
+    }
+
+    async fn set_entitlements(
+        &self,
+        id: Uuid,
+        entitlements: Option<&OrgEntitlements>,
+    ) -> DbResult<Organization> {
+        let entitlements = entitlements
+            .map(serde_json::to_value)
+            .transpose()
+            .map_err(|e| DbError::Internal(format!("failed to serialize entitlements: {e}")))?;
+
+        let row = sqlx::query(
+            r#"
+            UPDATE organizations
+            SET entitlements = $1, updated_at = NOW()
+            WHERE id = $2 AND deleted_at IS NULL
+            RETURNING id, slug, name, data_residency, request_defaults, conversation_summaries, network_policy, parameter_governance, model_access, model_degradation, entitlements, labels, created_at, updated_at
+            "#,
+        )
+        .bind(entitlements)
+        .bind(id)
+        .fetch_optional(&self.write_pool)
+        .await?
+        .ok_or(DbError::NotFound)?;
+
+        org_from_row(&row)
+    }
+
+    async fn set_labels(
+        &self,
+        id: Uuid,
+        labels: &BTreeMap<String, String>,
+    ) -> DbResult<Organization> {
+        let labels = serde_json::to_value(labels)
+            .map_err(|e| DbError::Internal(format!("failed to serialize labels: {e}")))?;
+
+        let row = sqlx::query(
+            r#"
+            UPDATE organizations
+            SET labels = $1, updated_at = NOW()
+            WHERE id = $2 AND deleted_at IS NULL
+            RETURNING id, slug, name, data_residency, request_defaults, conversation_summaries, network_policy, parameter_governance, model_access, model_degradation, entitlements, labels, created_at, updated_at
+            "#,
+        )
+        .bind(labels)
+        .bind(id)
+        .fetch_optional(&self.write_pool)
+        .await?
+        .ok_or(DbError::NotFound)?;
+
+        org_from_row(&row)
     }
 
     async fn delete(&self, id: Uuid) -> DbResult<()> {
@@ -1,5 +1,7 @@
+use std::collections::BTreeMap;
+
 use async_trait::async_trait;
-use sqlx::{PgPool, Row};
+use sqlx::{PgPool, Row, postgres::PgRow};
 use uuid::Uuid;
 
 use crate::{
@@ -10,9 +12,42 @@ use crate::{
             cursor_from_row,
         },
     },
-    models::{CreateProject, Project, UpdateProject},
+    models::{CreateProject, ModelAccessPolicy, ParameterGovernance, Project, UpdateProject},
 };
 
+fn project_from_row(row: &PgRow) -> DbResult<Project> {
+    Ok(Project {
+        id: row.get("id"),
+        org_id: row.get("org_id"),
+        team_id: row.get("team_id"),
+        slug: row.get("slug"),
+        name: row.get("name"),
+        request_defaults: row
+            .get::<Option<serde_json::Value>, _>("request_defaults")
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| {
+                DbError::Internal(format!("failed to deserialize request_defaults: {e}"))
+            })?,
+        parameter_governance: row
+            .get::<Option<serde_json::Value>, _>("parameter_governance")
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| {
+                DbError::Internal(format!("failed to deserialize parameter_governance: {e}"))
+            })?,
+        model_access: row
+            .get::<Option<serde_json::Value>, _>("model_access")
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| DbError::Internal(format!("failed to deserialize model_access: {e}")))?,
+        labels: serde_json::from_value(row.get("labels"))
+            .map_err(|e| DbError::Internal(format!("failed to deserialize labels: {e}")))?,
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    })
+}
+
 pub struct PostgresProjectRepo {
     write_pool: PgPool,
     read_pool: PgPool,
@@ -49,12 +84,13 @@ impl PostgresProjectRepo {
 
         let query = format!(
             r#"
-            SELECT id, org_id, team_id, slug, name, created_at, updated_at
+            SELECT id, org_id, team_id, slug, name, request_defaults, parameter_governance, model_access, labels, created_at, updated_at
             FROM projects
             WHERE org_id = $1 AND ROW(created_at, id) {} ROW($2, $3)
             {}
+            AND labels @> $4
             ORDER BY created_at {}, id {}
-            LIMIT $4
+            LIMIT $5
             "#,
             comparison, deleted_filter, order, order
         );
@@ -63,6 +99,7 @@ impl PostgresProjectRepo {
             .bind(org_id)
             .bind(cursor.created_at)
             .bind(cursor.id)
+            .bind(params.labels.to_json())
             .bind(fetch_limit)
             .fetch_all(&self.read_pool)
             .await?;
@@ -71,16 +108,8 @@ impl PostgresProjectRepo {
         let mut items: Vec<Project> = rows
             .into_iter()
             .take(limit as usize)
-            .map(|row| Project {
-                id: row.get("id"),
-                org_id: row.get("org_id"),
-                team_id: row.get("team_id"),
-                slug: row.get("slug"),
-                name: row.get("name"),
-                created_at: row.get("created_at"),
-                updated_at: row.get("updated_at"),
-            })
-            .collect();
+            .map(|row| project_from_row(&row))
+            .collect::<DbResult<Vec<_>>>()?;
 
         if should_reverse {
             items.reverse();
@@ -104,7 +133,7 @@ impl ProjectRepo for PostgresProjectRepo {
             r#"
             INSERT INTO projects (id, org_id, team_id, slug, name)
             VALUES ($1, $2, $3, $4, $5)
-            RETURNING id, org_id, team_id, slug, name, created_at, updated_at
+            RETURNING id, org_id, team_id, slug, name, request_defaults, parameter_governance, model_access, labels, created_at, updated_at
             "#,
         )
         .bind(Uuid::new_v4())
@@ -124,21 +153,13 @@ impl ProjectRepo for PostgresProjectRepo {
             _ => DbError::from(e),
         })?;
 
-        Ok(Project {
-            id: row.get("id"),
-            org_id: row.get("org_id"),
-            team_id: row.get("team_id"),
-            slug: row.get("slug"),
-            name: row.get("name"),
-            created_at: row.get("created_at"),
-            updated_at: row.get("updated_at"),
-        })
+        project_from_row(&row)
     }
 
     async fn get_by_id(&self, id: Uuid) -> DbResult<Option<Project>> {
         let result = sqlx::query(
             r#"
-            SELECT id, org_id, team_id, slug, name, created_at, updated_at
+            SELECT id, org_id, team_id, slug, name, request_defaults, parameter_governance, model_access, labels, created_at, updated_at
             FROM projects
             WHERE id = $1 AND deleted_at IS NULL
             "#,
@@ -147,21 +168,13 @@ impl ProjectRepo for PostgresProjectRepo {
         .fetch_optional(&self.read_pool)
         .await?;
 
-        Ok(result.map(|row| Project {
-            id: row.get("id"),
-            org_id: row.get("org_id"),
-            team_id: row.get("team_id"),
-            slug: row.get("slug"),
-            name: row.get("name"),
-            created_at: row.get("created_at"),
-            updated_at: row.get("updated_at"),
-        }))
+        result.map(|row| project_from_row(&row)).transpose()
     }
 
     async fn get_by_id_and_org(&self, id: Uuid, org_id: Uuid) -> DbResult<Option<Project>> {
         let result = sqlx::query(
             r#"
-            SELECT id, org_id, team_id, slug, name, created_at, updated_at
+            SELECT id, org_id, team_id, slug, name, request_defaults, parameter_governance, model_access, labels, created_at, updated_at
             FROM projects
             WHERE id = $1 AND org_id = $2 AND deleted_at IS NULL
             "#,
@@ -171,21 +184,13 @@ impl ProjectRepo for PostgresProjectRepo {
         .fetch_optional(&self.read_pool)
         .await?;
 
-        Ok(result.map(|row| Project {
-            id: row.get("id"),
-            org_id: row.get("org_id"),
-            team_id: row.get("team_id"),
-            slug: row.get("slug"),
-            name: row.get("name"),
-            created_at: row.get("created_at"),
-            updated_at: row.get("updated_at"),
-        }))
+        result.map(|row| project_from_row(&row)).transpose()
     }
 
     async fn get_by_slug(&self, org_id: Uuid, slug: &str) -> DbResult<Option<Project>> {
         let result = sqlx::query(
             r#"
-            SELECT id, org_id, team_id, slug, name, created_at, updated_at
+            SELECT id, org_id, team_id, slug, name, request_defaults, parameter_governance, model_access, labels, created_at, updated_at
             FROM projects
             WHERE org_id = $1 AND slug = $2 AND deleted_at IS NULL
             "#,
@@ -195,15 +200,7 @@ impl ProjectRepo for PostgresProjectRepo {
         .fetch_optional(&self.read_pool)
         .await?;
 
-        Ok(result.map(|row| Project {
-            id: row.get("id"),
-            org_id: row.get("org_id"),
-            team_id: row.get("team_id"),
-            slug: row.get("slug"),
-            name: row.get("name"),
-            created_at: row.get("created_at"),
-            updated_at: row.get("updated_at"),
-        }))
+        result.map(|row| project_from_row(&row)).transpose()
     }
 
     async fn list_by_org(&self, org_id: Uuid, params: ListParams) -> DbResult<ListResult<Project>> {
@@ -221,24 +218,27 @@ impl ProjectRepo for PostgresProjectRepo {
         // First page (no cursor provided)
         let query = if params.include_deleted {
             r#"
-            SELECT id, org_id, team_id, slug, name, created_at, updated_at
+            SELECT id, org_id, team_id, slug, name, request_defaults, parameter_governance, model_access, labels, created_at, updated_at
             FROM projects
             WHERE org_id = $1
+            AND labels @> $2
             ORDER BY created_at DESC, id DESC
-            LIMIT $2
+            LIMIT $3
             "#
         } else {
             r#"
-            SELECT id, org_id, team_id, slug, name, created_at, updated_at
+            SELECT id, org_id, team_id, slug, name, request_defaults, parameter_governance, model_access, labels, created_at, updated_at
             FROM projects
             WHERE org_id = $1 AND deleted_at IS NULL
+            AND labels @> $2
             ORDER BY created_at DESC, id DESC
-            LIMIT $2
+            LIMIT $3
             "#
         };
 
         let rows = sqlx::query(query)
             .bind(org_id)
+            .bind(params.labels.to_json())
             .bind(fetch_limit)
             .fetch_all(&self.read_pool)
             .await?;
@@ -247,16 +247,8 @@ impl ProjectRepo for PostgresProjectRepo {
         let items: Vec<Project> = rows
             .into_iter()
             .take(limit as usize)
-            .map(|row| Project {
-                id: row.get("id"),
-                org_id: row.get("org_id"),
-                team_id: row.get("team_id"),
-                slug: row.get("slug"),
-                name: row.get("name"),
-                created_at: row.get("created_at"),
-                updated_at: row.get("updated_at"),
-            })
-            .collect();
+            .map(|row| project_from_row(&row))
+            .collect::<DbResult<Vec<_>>>()?;
 
         // Generate cursors for pagination
         let cursors =
@@ -308,8 +300,9 @@ impl ProjectRepo for PostgresProjectRepo {
     async fn update(&self, id: Uuid, input: UpdateProject) -> DbResult<Project> {
         let has_name_update = input.name.is_some();
         let has_team_update = input.team_id.is_some();
+        let has_defaults_update = input.request_defaults.is_some();
 
-        if !has_name_update && !has_team_update {
+        if !has_name_update && !has_team_update && !has_defaults_update {
             return self.get_by_id(id).await?.ok_or(DbError::NotFound);
         }
 
@@ -324,13 +317,17 @@ impl ProjectRepo for PostgresProjectRepo {
             set_clauses.push(format!("team_id = ${}", param_idx));
             param_idx += 1;
         }
+        if has_defaults_update {
+            set_clauses.push(format!("request_defaults = ${}", param_idx));
+            param_idx += 1;
+        }
 
         let query = format!(
             r#"
             UPDATE projects
             SET {}
             WHERE id = ${} AND deleted_at IS NULL
-            RETURNING id, org_id, team_id, slug, name, created_at, updated_at
+            RETURNING id, org_id, team_id, slug, name, request_defaults, parameter_governance, model_access, labels, created_at, updated_at
             "#,
             set_clauses.join(", "),
             param_idx
@@ -344,6 +341,10 @@ impl ProjectRepo for PostgresProjectRepo {
         if let Some(ref team_id_opt) = input.team_id {
             query_builder = query_builder.bind(*team_id_opt);
         }
+        if let Some(ref defaults) = input.request_defaults {
+            query_builder =
+                query_builder.bind(defaults.as_ref().and_then(|d| serde_json::to_value(d).ok()));
+        }
 
         let row = query_builder
             .bind(id)
@@ -351,15 +352,84 @@ impl ProjectRepo for PostgresProjectRepo {
             .await?
             .ok_or(DbError::NotFound)?;
 
-        Ok(Project {
-            id: row.get("id"),
-            org_id: row.get("org_id"),
-            team_id: row.get("team_id"),
-            slug: row.get("slug"),
-            name: row.get("name"),
-            created_at: row.get("created_at"),
-            updated_at: row.get("updated_at"),
-        })
+        project_from_row(&row)
+    }
+
+    async fn set_parameter_governance(
+        &self,
+        id: Uuid,
+        governance: Option<&ParameterGovernance>,
+    ) -> DbResult<Project> {
+        let governance = governance
+            .map(serde_json::to_value)
+            .transpose()
+            .map_err(|e| {
+                DbError::Internal(format!("failed to serialize parameter_governance: {e}"))
+            })?;
+
+        let row = sqlx::query(
+            r#"
+            UPDATE projects
+            SET parameter_governance = $1, updated_at = NOW()
+            WHERE id = $2 AND deleted_at IS NULL
+            RETURNING id, org_id, team_id, slug, name, request_defaults, parameter_governance, model_access, labels, created_at, updated_at
+            "#,
+        )
+        .bind(governance)
+        .bind(id)
+        .fetch_optional(&self.write_pool)
+        .await?
+        .ok_or(DbError::NotFound)?;
+
+        project_from_row(&row)
+    }
+
+    async fn set_model_access(
+        &self,
+        id: Uuid,
+        policy: Option<&ModelAccessPolicy>,
+    ) -> DbResult<Project> {
+        let policy = policy
+            .map(serde_json::to_value)
+            .transpose()
+            .map_err(|e| DbError::Internal(format!("failed to serialize model_access: {e}")))?;
+
+        let row = sqlx::query(
+            r#"
+            UPDATE projects
+            SET model_access = $1, updated_at = NOW()
+            WHERE id = $2 AND deleted_at IS NULL
+            RETURNING id, org_id, team_id, slug, name, request_defaults, parameter_governance, model_access, labels, created_at, updated_at
+            "#,
+        )
+        .bind(policy)
+        .bind(id)
+        .fetch_optional(&self.write_pool)
+        .await?
+        .ok_or(DbError::NotFound)?;
+
+        project_from_row(&row)
+    }
+
+    async fn set_labels(&self, id: Uuid, labels: &BTreeMap<String, String>) -> DbResult<Project> {
+        let labels = serde_json::to_value(labels)
+            .map_err(|e| DbError::Internal(format!("failed to serialize labels: {e}")))?;
+
+        let row = sqlx::query(
+            r#"
+            UPDATE projects
+            SET labels = $1, updated_at = NOW()
+            WHERE id = $2 AND deleted_at IS NULL
+            RETURNING id, org_id, team_id, slug, name, request_defaults, parameter_governance, model_access, labels, created_at, updated_at
+            "#,
+        )
+        .bind(labels)
+        .bind(id)
+        .fetch_optional(&self.write_pool)
+        .await?
+        .ok_or(DbError::NotFound)?;
+
+        project_from_row(&row)
     }
 
     async fn delete(&self, id: Uuid) -> DbResult<()> {
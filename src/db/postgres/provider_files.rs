@@ -0,0 +1,118 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::{PgPool, Row, postgres::PgRow};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{ProviderFileRepo, truncate_to_millis},
+    },
+    models::ProviderFile,
+};
+
+const COLUMNS: &str = "id, file_id, provider, provider_file_id, created_at";
+
+pub struct PostgresProviderFileRepo {
+    write_pool: PgPool,
+    read_pool: PgPool,
+}
+
+impl PostgresProviderFileRepo {
+    pub fn new(write_pool: PgPool, read_pool: Option<PgPool>) -> Self {
+        let read_pool = read_pool.unwrap_or_else(|| write_pool.clone());
+        Self {
+            write_pool,
+            read_pool,
+        }
+    }
+
+    fn parse_provider_file(row: &PgRow) -> ProviderFile {
+        ProviderFile {
+            id: row.get("id"),
+            file_id: row.get("file_id"),
+            provider: row.get("provider"),
+            provider_file_id: row.get("provider_file_id"),
+            created_at: row.get("created_at"),
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl ProviderFileRepo for PostgresProviderFileRepo {
+    async fn create(
+        &self,
+        file_id: Uuid,
+        provider: &str,
+        provider_file_id: &str,
+    ) -> DbResult<ProviderFile> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+
+        sqlx::query(
+            r#"
+            INSERT INTO provider_files (id, file_id, provider, provider_file_id, created_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(id)
+        .bind(file_id)
+        .bind(provider)
+        .bind(provider_file_id)
+        .bind(now)
+        .execute(&self.write_pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db_err) if db_err.is_unique_violation() => DbError::Conflict(
+                format!("File '{file_id}' already has a copy at provider '{provider}'"),
+            ),
+            _ => DbError::from(e),
+        })?;
+
+        Ok(ProviderFile {
+            id,
+            file_id,
+            provider: provider.to_string(),
+            provider_file_id: provider_file_id.to_string(),
+            created_at: now,
+        })
+    }
+
+    async fn get(&self, file_id: Uuid, provider: &str) -> DbResult<Option<ProviderFile>> {
+        let sql =
+            format!("SELECT {COLUMNS} FROM provider_files WHERE file_id = $1 AND provider = $2");
+        let row = sqlx::query(&sql)
+            .bind(file_id)
+            .bind(provider)
+            .fetch_optional(&self.read_pool)
+            .await?;
+
+        Ok(row.as_ref().map(Self::parse_provider_file))
+    }
+
+    async fn list_by_file(&self, file_id: Uuid) -> DbResult<Vec<ProviderFile>> {
+        let sql = format!(
+            "SELECT {COLUMNS} FROM provider_files WHERE file_id = $1 \
+             ORDER BY created_at ASC, id ASC"
+        );
+        let rows = sqlx::query(&sql)
+            .bind(file_id)
+            .fetch_all(&self.read_pool)
+            .await?;
+
+        Ok(rows.iter().map(Self::parse_provider_file).collect())
+    }
+
+    async fn delete(&self, id: Uuid) -> DbResult<()> {
+        let result = sqlx::query("DELETE FROM provider_files WHERE id = $1")
+            .bind(id)
+            .execute(&self.write_pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+        Ok(())
+    }
+}
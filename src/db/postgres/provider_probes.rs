@@ -0,0 +1,133 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{ProviderProbeRepo, truncate_to_millis},
+    },
+    models::{CreateProviderProbeResult, ProbeBucketStats, ProviderProbeResult},
+};
+
+pub struct PostgresProviderProbeRepo {
+    write_pool: PgPool,
+    read_pool: PgPool,
+}
+
+impl PostgresProviderProbeRepo {
+    pub fn new(write_pool: PgPool, read_pool: Option<PgPool>) -> Self {
+        let read_pool = read_pool.unwrap_or_else(|| write_pool.clone());
+        Self {
+            write_pool,
+            read_pool,
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl ProviderProbeRepo for PostgresProviderProbeRepo {
+    async fn create(&self, input: CreateProviderProbeResult) -> DbResult<ProviderProbeResult> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+
+        sqlx::query(
+            r#"
+            INSERT INTO provider_probe_results (
+                id, provider, model, success, correct, latency_ms,
+                input_tokens, output_tokens, error, created_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            "#,
+        )
+        .bind(id)
+        .bind(&input.provider)
+        .bind(&input.model)
+        .bind(input.success)
+        .bind(input.correct)
+        .bind(input.latency_ms)
+        .bind(input.input_tokens)
+        .bind(input.output_tokens)
+        .bind(&input.error)
+        .bind(now)
+        .execute(&self.write_pool)
+        .await?;
+
+        Ok(ProviderProbeResult {
+            id,
+            provider: input.provider,
+            model: input.model,
+            success: input.success,
+            correct: input.correct,
+            latency_ms: input.latency_ms,
+            input_tokens: input.input_tokens,
+            output_tokens: input.output_tokens,
+            error: input.error,
+            created_at: now,
+        })
+    }
+
+    async fn history(
+        &self,
+        provider: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        bucket_secs: i64,
+    ) -> DbResult<Vec<ProbeBucketStats>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                (FLOOR(EXTRACT(EPOCH FROM created_at) / $1) * $1)::BIGINT as bucket,
+                model,
+                COUNT(*) as probe_count,
+                COUNT(*) FILTER (WHERE success) as success_count,
+                COUNT(*) FILTER (WHERE correct) as correct_count,
+                (AVG(latency_ms) FILTER (WHERE success))::DOUBLE PRECISION as avg_latency_ms,
+                MAX(latency_ms) FILTER (WHERE success) as max_latency_ms,
+                (AVG(output_tokens) FILTER (WHERE success))::DOUBLE PRECISION as avg_output_tokens
+            FROM provider_probe_results
+            WHERE provider = $2 AND created_at >= $3 AND created_at < $4
+            GROUP BY bucket, model
+            ORDER BY bucket ASC, model ASC
+            "#,
+        )
+        .bind(bucket_secs)
+        .bind(provider)
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                let bucket: i64 = row.get("bucket");
+                Ok(ProbeBucketStats {
+                    bucket_start: DateTime::from_timestamp(bucket, 0).ok_or_else(|| {
+                        DbError::Internal(format!("Invalid probe bucket timestamp: {bucket}"))
+                    })?,
+                    model: row.get("model"),
+                    probe_count: row.get("probe_count"),
+                    success_count: row.get("success_count"),
+                    correct_count: row.get("correct_count"),
+                    avg_latency_ms: row.get("avg_latency_ms"),
+                    max_latency_ms: row.get("max_latency_ms"),
+                    avg_output_tokens: row.get("avg_output_tokens"),
+                })
+            })
+            .collect()
+    }
+
+    async fn delete_before(&self, provider: &str, cutoff: DateTime<Utc>) -> DbResult<u64> {
+        let result = sqlx::query(
+            "DELETE FROM provider_probe_results WHERE provider = $1 AND created_at < $2",
+        )
+        .bind(provider)
+        .bind(cutoff)
+        .execute(&self.write_pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
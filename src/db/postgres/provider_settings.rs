@@ -0,0 +1,136 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::de::DeserializeOwned;
+use sqlx::{PgPool, Row, postgres::PgRow};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{ProviderSettingsRepo, truncate_to_millis},
+    },
+    models::{ProviderSettings, UpdateProviderSettings},
+};
+
+const COLUMNS: &str = "id, provider_name, enabled, circuit_breaker, retry, max_concurrent, \
+                       updated_by, created_at, updated_at";
+
+pub struct PostgresProviderSettingsRepo {
+    write_pool: PgPool,
+    read_pool: PgPool,
+}
+
+impl PostgresProviderSettingsRepo {
+    pub fn new(write_pool: PgPool, read_pool: Option<PgPool>) -> Self {
+        let read_pool = read_pool.unwrap_or_else(|| write_pool.clone());
+        Self {
+            write_pool,
+            read_pool,
+        }
+    }
+
+    fn parse_json<T: DeserializeOwned>(row: &PgRow, column: &str) -> DbResult<Option<T>> {
+        row.get::<Option<serde_json::Value>, _>(column)
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| DbError::Internal(format!("failed to deserialize {column}: {e}")))
+    }
+
+    fn parse_settings(row: &PgRow) -> DbResult<ProviderSettings> {
+        Ok(ProviderSettings {
+            id: row.get("id"),
+            provider_name: row.get("provider_name"),
+            enabled: row.get("enabled"),
+            circuit_breaker: Self::parse_json(row, "circuit_breaker")?,
+            retry: Self::parse_json(row, "retry")?,
+            max_concurrent: row
+                .get::<Option<i32>, _>("max_concurrent")
+                .map(|v| v as u32),
+            updated_by: row.get("updated_by"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl ProviderSettingsRepo for PostgresProviderSettingsRepo {
+    async fn get(&self, provider_name: &str) -> DbResult<Option<ProviderSettings>> {
+        let sql = format!("SELECT {COLUMNS} FROM provider_settings WHERE provider_name = $1");
+        let row = sqlx::query(&sql)
+            .bind(provider_name)
+            .fetch_optional(&self.read_pool)
+            .await?;
+
+        row.map(|r| Self::parse_settings(&r)).transpose()
+    }
+
+    async fn list(&self) -> DbResult<Vec<ProviderSettings>> {
+        let sql = format!("SELECT {COLUMNS} FROM provider_settings ORDER BY provider_name ASC");
+        let rows = sqlx::query(&sql).fetch_all(&self.read_pool).await?;
+
+        rows.iter().map(Self::parse_settings).collect()
+    }
+
+    async fn upsert(
+        &self,
+        provider_name: &str,
+        input: UpdateProviderSettings,
+        updated_by: Option<Uuid>,
+    ) -> DbResult<ProviderSettings> {
+        let now = truncate_to_millis(Utc::now());
+        let circuit_breaker = input
+            .circuit_breaker
+            .as_ref()
+            .map(serde_json::to_value)
+            .transpose()
+            .map_err(|e| DbError::Internal(format!("failed to serialize circuit_breaker: {e}")))?;
+        let retry = input
+            .retry
+            .as_ref()
+            .map(serde_json::to_value)
+            .transpose()
+            .map_err(|e| DbError::Internal(format!("failed to serialize retry: {e}")))?;
+
+        let sql = format!(
+            r#"
+            INSERT INTO provider_settings (
+                id, provider_name, enabled, circuit_breaker, retry, max_concurrent,
+                updated_by, created_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $8)
+            ON CONFLICT (provider_name) DO UPDATE SET
+                enabled = EXCLUDED.enabled,
+                circuit_breaker = EXCLUDED.circuit_breaker,
+                retry = EXCLUDED.retry,
+                max_concurrent = EXCLUDED.max_concurrent,
+                updated_by = EXCLUDED.updated_by,
+                updated_at = EXCLUDED.updated_at
+            RETURNING {COLUMNS}
+            "#
+        );
+        let row = sqlx::query(&sql)
+            .bind(Uuid::new_v4())
+            .bind(provider_name)
+            .bind(input.enabled)
+            .bind(circuit_breaker)
+            .bind(retry)
+            .bind(input.max_concurrent.map(|v| v as i32))
+            .bind(updated_by)
+            .bind(now)
+            .fetch_one(&self.write_pool)
+            .await?;
+
+        Self::parse_settings(&row)
+    }
+
+    async fn delete(&self, provider_name: &str) -> DbResult<bool> {
+        let result = sqlx::query("DELETE FROM provider_settings WHERE provider_name = $1")
+            .bind(provider_name)
+            .execute(&self.write_pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
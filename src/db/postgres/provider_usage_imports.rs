@@ -0,0 +1,183 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::{PgPool, Row, postgres::PgRow};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{DateRange, ProviderUsageImportRepo, truncate_to_millis},
+    },
+    models::{CreateProviderUsageImport, ImportedUsage, ImportedUsageRow, ProviderUsageImport},
+};
+
+const IMPORT_COLUMNS: &str =
+    "id, provider, source, method, start_date, end_date, row_count, created_at";
+
+pub struct PostgresProviderUsageImportRepo {
+    write_pool: PgPool,
+    read_pool: PgPool,
+}
+
+impl PostgresProviderUsageImportRepo {
+    pub fn new(write_pool: PgPool, read_pool: Option<PgPool>) -> Self {
+        let read_pool = read_pool.unwrap_or_else(|| write_pool.clone());
+        Self {
+            write_pool,
+            read_pool,
+        }
+    }
+
+    fn parse_import(row: &PgRow) -> DbResult<ProviderUsageImport> {
+        Ok(ProviderUsageImport {
+            id: row.get("id"),
+            provider: row.get("provider"),
+            source: row
+                .get::<String, _>("source")
+                .parse()
+                .map_err(DbError::Internal)?,
+            method: row
+                .get::<String, _>("method")
+                .parse()
+                .map_err(DbError::Internal)?,
+            start_date: row.get("start_date"),
+            end_date: row.get("end_date"),
+            row_count: i64::from(row.get::<i32, _>("row_count")),
+            created_at: row.get("created_at"),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl ProviderUsageImportRepo for PostgresProviderUsageImportRepo {
+    async fn record_import(
+        &self,
+        input: CreateProviderUsageImport,
+        usage: &[ImportedUsage],
+    ) -> DbResult<ProviderUsageImport> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+        let row_count = usage.len() as i64;
+
+        let mut tx = self.write_pool.begin().await?;
+
+        let sql = format!(
+            "INSERT INTO provider_usage_imports ({IMPORT_COLUMNS}) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"
+        );
+        sqlx::query(&sql)
+            .bind(id)
+            .bind(&input.provider)
+            .bind(input.source.as_str())
+            .bind(input.method.as_str())
+            .bind(input.start_date)
+            .bind(input.end_date)
+            .bind(i32::try_from(row_count).unwrap_or(i32::MAX))
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query("DELETE FROM provider_usage WHERE provider = $1 AND date >= $2 AND date <= $3")
+            .bind(&input.provider)
+            .bind(input.start_date)
+            .bind(input.end_date)
+            .execute(&mut *tx)
+            .await?;
+
+        for usage in usage {
+            sqlx::query(
+                r#"
+                INSERT INTO provider_usage (
+                    provider, date, model, import_id, request_count, input_tokens,
+                    output_tokens, cost_microcents
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                "#,
+            )
+            .bind(&input.provider)
+            .bind(usage.date)
+            .bind(&usage.model)
+            .bind(id)
+            .bind(usage.request_count)
+            .bind(usage.input_tokens)
+            .bind(usage.output_tokens)
+            .bind(usage.cost_microcents)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(ProviderUsageImport {
+            id,
+            provider: input.provider,
+            source: input.source,
+            method: input.method,
+            start_date: input.start_date,
+            end_date: input.end_date,
+            row_count,
+            created_at: now,
+        })
+    }
+
+    async fn list_imports(&self, provider: Option<&str>) -> DbResult<Vec<ProviderUsageImport>> {
+        let sql = format!(
+            "SELECT {IMPORT_COLUMNS} FROM provider_usage_imports \
+             WHERE ($1::TEXT IS NULL OR provider = $1) \
+             ORDER BY created_at DESC, id DESC"
+        );
+        let rows = sqlx::query(&sql)
+            .bind(provider)
+            .fetch_all(&self.read_pool)
+            .await?;
+
+        rows.iter().map(Self::parse_import).collect()
+    }
+
+    async fn delete_import(&self, id: Uuid) -> DbResult<bool> {
+        let result = sqlx::query("DELETE FROM provider_usage_imports WHERE id = $1")
+            .bind(id)
+            .execute(&self.write_pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list_usage(
+        &self,
+        range: DateRange,
+        provider: Option<&str>,
+    ) -> DbResult<Vec<ImportedUsageRow>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT provider, date, model, import_id, request_count, input_tokens,
+                   output_tokens, cost_microcents
+            FROM provider_usage
+            WHERE date >= $1 AND date <= $2
+              AND ($3::TEXT IS NULL OR provider = $3)
+            ORDER BY provider ASC, date ASC, model ASC
+            "#,
+        )
+        .bind(range.start)
+        .bind(range.end)
+        .bind(provider)
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| ImportedUsageRow {
+                provider: row.get("provider"),
+                import_id: row.get("import_id"),
+                usage: ImportedUsage {
+                    date: row.get("date"),
+                    model: row.get("model"),
+                    request_count: row.get("request_count"),
+                    input_tokens: row.get("input_tokens"),
+                    output_tokens: row.get("output_tokens"),
+                    cost_microcents: row.get("cost_microcents"),
+                },
+            })
+            .collect())
+    }
+}
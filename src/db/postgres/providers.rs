@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use async_trait::async_trait;
 use sqlx::{PgPool, Row};
 use uuid::Uuid;
@@ -73,6 +75,8 @@ impl PostgresDynamicProviderRepo {
                     DbError::Internal(format!("failed to deserialize sovereignty metadata: {e}"))
                 })?,
             is_enabled: row.get("is_enabled"),
+            labels: serde_json::from_value(row.get("labels"))
+                .map_err(|e| DbError::Internal(format!("failed to deserialize labels: {e}")))?,
             created_at: row.get("created_at"),
             updated_at: row.get("updated_at"),
         })
@@ -93,12 +97,13 @@ impl PostgresDynamicProviderRepo {
         let query = format!(
             r#"
             SELECT id, owner_type::TEXT, owner_id, name, provider_type, base_url,
-                   api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                   api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
             FROM dynamic_providers
             WHERE owner_type = 'organization' AND owner_id = $1
             AND ROW(created_at, id) {} ROW($2, $3)
+            AND labels @> $4
             ORDER BY created_at {}, id {}
-            LIMIT $4
+            LIMIT $5
             "#,
             comparison, order, order
         );
@@ -107,6 +112,7 @@ impl PostgresDynamicProviderRepo {
             .bind(org_id)
             .bind(cursor.created_at)
             .bind(cursor.id)
+            .bind(params.labels.to_json())
             .bind(fetch_limit)
             .fetch_all(&self.read_pool)
             .await?;
@@ -145,12 +151,13 @@ impl PostgresDynamicProviderRepo {
         let query = format!(
             r#"
             SELECT id, owner_type::TEXT, owner_id, name, provider_type, base_url,
-                   api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                   api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
             FROM dynamic_providers
             WHERE owner_type = 'project' AND owner_id = $1
             AND ROW(created_at, id) {} ROW($2, $3)
+            AND labels @> $4
             ORDER BY created_at {}, id {}
-            LIMIT $4
+            LIMIT $5
             "#,
             comparison, order, order
         );
@@ -159,6 +166,7 @@ impl PostgresDynamicProviderRepo {
             .bind(project_id)
             .bind(cursor.created_at)
             .bind(cursor.id)
+            .bind(params.labels.to_json())
             .bind(fetch_limit)
             .fetch_all(&self.read_pool)
             .await?;
@@ -197,12 +205,13 @@ impl PostgresDynamicProviderRepo {
         let query = format!(
             r#"
             SELECT id, owner_type::TEXT, owner_id, name, provider_type, base_url,
-                   api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                   api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
             FROM dynamic_providers
             WHERE owner_type = 'team' AND owner_id = $1
             AND ROW(created_at, id) {} ROW($2, $3)
+            AND labels @> $4
             ORDER BY created_at {}, id {}
-            LIMIT $4
+            LIMIT $5
             "#,
             comparison, order, order
         );
@@ -211,6 +220,7 @@ impl PostgresDynamicProviderRepo {
             .bind(team_id)
             .bind(cursor.created_at)
             .bind(cursor.id)
+            .bind(params.labels.to_json())
             .bind(fetch_limit)
             .fetch_all(&self.read_pool)
             .await?;
@@ -249,12 +259,13 @@ impl PostgresDynamicProviderRepo {
         let query = format!(
             r#"
             SELECT id, owner_type::TEXT, owner_id, name, provider_type, base_url,
-                   api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                   api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
             FROM dynamic_providers
             WHERE owner_type = 'user' AND owner_id = $1
             AND ROW(created_at, id) {} ROW($2, $3)
+            AND labels @> $4
             ORDER BY created_at {}, id {}
-            LIMIT $4
+            LIMIT $5
             "#,
             comparison, order, order
         );
@@ -263,6 +274,7 @@ impl PostgresDynamicProviderRepo {
             .bind(user_id)
             .bind(cursor.created_at)
             .bind(cursor.id)
+            .bind(params.labels.to_json())
             .bind(fetch_limit)
             .fetch_all(&self.read_pool)
             .await?;
@@ -340,6 +352,7 @@ impl DynamicProviderRepo for PostgresDynamicProviderRepo {
             config: input.config,
             models,
             sovereignty: input.sovereignty,
+            labels: Default::default(),
             is_enabled: true,
             created_at: row.get("created_at"),
             updated_at: row.get("updated_at"),
@@ -350,7 +363,7 @@ impl DynamicProviderRepo for PostgresDynamicProviderRepo {
         let row = sqlx::query(
             r#"
             SELECT id, owner_type::TEXT, owner_id, name, provider_type, base_url,
-                   api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                   api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
             FROM dynamic_providers
             WHERE id = $1
             "#,
@@ -372,7 +385,7 @@ impl DynamicProviderRepo for PostgresDynamicProviderRepo {
         let row = sqlx::query(
             r#"
             SELECT id, owner_type::TEXT, owner_id, name, provider_type, base_url,
-                   api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                   api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
             FROM dynamic_providers
             WHERE owner_type = $1::dynamic_provider_owner_type AND owner_id = $2 AND name = $3
             "#,
@@ -405,14 +418,16 @@ impl DynamicProviderRepo for PostgresDynamicProviderRepo {
         let rows = sqlx::query(
             r#"
             SELECT id, owner_type::TEXT, owner_id, name, provider_type, base_url,
-                   api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                   api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
             FROM dynamic_providers
             WHERE owner_type = 'organization' AND owner_id = $1
+            AND labels @> $2
             ORDER BY created_at DESC, id DESC
-            LIMIT $2
+            LIMIT $3
             "#,
         )
         .bind(org_id)
+        .bind(params.labels.to_json())
         .bind(fetch_limit)
         .fetch_all(&self.read_pool)
         .await?;
@@ -465,14 +480,16 @@ impl DynamicProviderRepo for PostgresDynamicProviderRepo {
         let rows = sqlx::query(
             r#"
             SELECT id, owner_type::TEXT, owner_id, name, provider_type, base_url,
-                   api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                   api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
             FROM dynamic_providers
             WHERE owner_type = 'team' AND owner_id = $1
+            AND labels @> $2
             ORDER BY created_at DESC, id DESC
-            LIMIT $2
+            LIMIT $3
             "#,
         )
         .bind(team_id)
+        .bind(params.labels.to_json())
         .bind(fetch_limit)
         .fetch_all(&self.read_pool)
         .await?;
@@ -525,14 +542,16 @@ impl DynamicProviderRepo for PostgresDynamicProviderRepo {
         let rows = sqlx::query(
             r#"
             SELECT id, owner_type::TEXT, owner_id, name, provider_type, base_url,
-                   api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                   api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
             FROM dynamic_providers
             WHERE owner_type = 'project' AND owner_id = $1
+            AND labels @> $2
             ORDER BY created_at DESC, id DESC
-            LIMIT $2
+            LIMIT $3
             "#,
         )
         .bind(project_id)
+        .bind(params.labels.to_json())
         .bind(fetch_limit)
         .fetch_all(&self.read_pool)
         .await?;
@@ -585,14 +604,16 @@ impl DynamicProviderRepo for PostgresDynamicProviderRepo {
         let rows = sqlx::query(
             r#"
             SELECT id, owner_type::TEXT, owner_id, name, provider_type, base_url,
-                   api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                   api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
             FROM dynamic_providers
             WHERE owner_type = 'user' AND owner_id = $1
+            AND labels @> $2
             ORDER BY created_at DESC, id DESC
-            LIMIT $2
+            LIMIT $3
             "#,
         )
         .bind(user_id)
+        .bind(params.labels.to_json())
         .bind(fetch_limit)
         .fetch_all(&self.read_pool)
         .await?;
@@ -641,7 +662,7 @@ impl DynamicProviderRepo for PostgresDynamicProviderRepo {
             let query = format!(
                 r#"
                 SELECT id, owner_type::TEXT, owner_id, name, provider_type, base_url,
-                       api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                       api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
                 FROM dynamic_providers
                 WHERE owner_type = 'user' AND owner_id = $1 AND is_enabled = true
                 AND ROW(created_at, id) {} ROW($2, $3)
@@ -681,7 +702,7 @@ impl DynamicProviderRepo for PostgresDynamicProviderRepo {
         let rows = sqlx::query(
             r#"
             SELECT id, owner_type::TEXT, owner_id, name, provider_type, base_url,
-                   api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                   api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
             FROM dynamic_providers
             WHERE owner_type = 'user' AND owner_id = $1 AND is_enabled = true
             ORDER BY created_at DESC, id DESC
@@ -722,7 +743,7 @@ impl DynamicProviderRepo for PostgresDynamicProviderRepo {
             let query = format!(
                 r#"
                 SELECT id, owner_type::TEXT, owner_id, name, provider_type, base_url,
-                       api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                       api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
                 FROM dynamic_providers
                 WHERE owner_type = 'organization' AND owner_id = $1 AND is_enabled = true
                 AND ROW(created_at, id) {} ROW($2, $3)
@@ -762,7 +783,7 @@ impl DynamicProviderRepo for PostgresDynamicProviderRepo {
         let rows = sqlx::query(
             r#"
             SELECT id, owner_type::TEXT, owner_id, name, provider_type, base_url,
-                   api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                   api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
             FROM dynamic_providers
             WHERE owner_type = 'organization' AND owner_id = $1 AND is_enabled = true
             ORDER BY created_at DESC, id DESC
@@ -803,7 +824,7 @@ impl DynamicProviderRepo for PostgresDynamicProviderRepo {
             let query = format!(
                 r#"
                 SELECT id, owner_type::TEXT, owner_id, name, provider_type, base_url,
-                       api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                       api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
                 FROM dynamic_providers
                 WHERE owner_type = 'project' AND owner_id = $1 AND is_enabled = true
                 AND ROW(created_at, id) {} ROW($2, $3)
@@ -843,7 +864,7 @@ impl DynamicProviderRepo for PostgresDynamicProviderRepo {
         let rows = sqlx::query(
             r#"
             SELECT id, owner_type::TEXT, owner_id, name, provider_type, base_url,
-                   api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                   api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
             FROM dynamic_providers
             WHERE owner_type = 'project' AND owner_id = $1 AND is_enabled = true
             ORDER BY created_at DESC, id DESC
@@ -884,7 +905,7 @@ impl DynamicProviderRepo for PostgresDynamicProviderRepo {
             let query = format!(
                 r#"
                 SELECT id, owner_type::TEXT, owner_id, name, provider_type, base_url,
-                       api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                       api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
                 FROM dynamic_providers
                 WHERE owner_type = 'team' AND owner_id = $1 AND is_enabled = true
                 AND ROW(created_at, id) {} ROW($2, $3)
@@ -924,7 +945,7 @@ impl DynamicProviderRepo for PostgresDynamicProviderRepo {
         let rows = sqlx::query(
             r#"
             SELECT id, owner_type::TEXT, owner_id, name, provider_type, base_url,
-                   api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                   api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
             FROM dynamic_providers
             WHERE owner_type = 'team' AND owner_id = $1 AND is_enabled = true
             ORDER BY created_at DESC, id DESC
@@ -1027,6 +1048,32 @@ impl DynamicProviderRepo for PostgresDynamicProviderRepo {
         self.get_by_id(id).await?.ok_or(DbError::NotFound)
     }
 
+    async fn set_labels(
+        &self,
+        id: Uuid,
+        labels: &BTreeMap<String, String>,
+    ) -> DbResult<DynamicProvider> {
+        let labels = serde_json::to_value(labels)
+            .map_err(|e| DbError::Internal(format!("failed to serialize labels: {e}")))?;
+
+        let row = sqlx::query(
+            r#"
+            UPDATE dynamic_providers
+            SET labels = $1, updated_at = NOW()
+            WHERE id = $2
+            RETURNING id, owner_type::TEXT, owner_id, name, provider_type, base_url,
+                api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
+            "#,
+        )
+        .bind(labels)
+        .bind(id)
+        .fetch_optional(&self.write_pool)
+        .await?
+        .ok_or(DbError::NotFound)?;
+
+        Self::parse_provider(&row)
+    }
+
     async fn delete(&self, id: Uuid) -> DbResult<()> {
         sqlx::query("DELETE FROM dynamic_providers WHERE id = $1")
             .bind(id)
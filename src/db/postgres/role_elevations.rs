@@ -0,0 +1,300 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Row, postgres::PgRow};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{
+            CursorDirection, ListParams, ListResult, PageCursors, RoleElevationRepo,
+            cursor_from_row, truncate_to_millis,
+        },
+    },
+    models::{ElevationStatus, NewRoleElevation, RoleElevation},
+};
+
+const COLUMNS: &str = "id, org_id, user_id, role, previous_role, justification, duration_hours, \
+                       status, requested_by, decided_by, decision_note, decided_at, expires_at, \
+                       ended_at, ended_by, created_at, updated_at";
+
+pub struct PostgresRoleElevationRepo {
+    write_pool: PgPool,
+    read_pool: PgPool,
+}
+
+impl PostgresRoleElevationRepo {
+    pub fn new(write_pool: PgPool, read_pool: Option<PgPool>) -> Self {
+        let read_pool = read_pool.unwrap_or_else(|| write_pool.clone());
+        Self {
+            write_pool,
+            read_pool,
+        }
+    }
+
+    fn parse_elevation(row: &PgRow) -> DbResult<RoleElevation> {
+        Ok(RoleElevation {
+            id: row.get("id"),
+            org_id: row.get("org_id"),
+            user_id: row.get("user_id"),
+            role: row.get("role"),
+            previous_role: row.get("previous_role"),
+            justification: row.get("justification"),
+            duration_hours: row.get("duration_hours"),
+            status: row
+                .get::<String, _>("status")
+                .parse()
+                .map_err(DbError::Internal)?,
+            requested_by: row.get("requested_by"),
+            decided_by: row.get("decided_by"),
+            decision_note: row.get("decision_note"),
+            decided_at: row.get("decided_at"),
+            expires_at: row.get("expires_at"),
+            ended_at: row.get("ended_at"),
+            ended_by: row.get("ended_by"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        })
+    }
+
+    /// Read an elevation back from the primary after a write.
+    async fn get_fresh(&self, id: Uuid) -> DbResult<RoleElevation> {
+        let sql = format!("SELECT {COLUMNS} FROM role_elevations WHERE id = $1");
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .fetch_optional(&self.write_pool)
+            .await?
+            .ok_or(DbError::NotFound)?;
+
+        Self::parse_elevation(&row)
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl RoleElevationRepo for PostgresRoleElevationRepo {
+    async fn create(&self, input: NewRoleElevation) -> DbResult<RoleElevation> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+
+        sqlx::query(
+            r#"
+            INSERT INTO role_elevations (
+                id, org_id, user_id, role, justification, duration_hours, status,
+                requested_by, created_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, 'pending', $7, $8, $8)
+            "#,
+        )
+        .bind(id)
+        .bind(input.org_id)
+        .bind(input.user_id)
+        .bind(&input.role)
+        .bind(&input.justification)
+        .bind(input.duration_hours)
+        .bind(input.requested_by)
+        .bind(now)
+        .execute(&self.write_pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db_err) if db_err.is_unique_violation() => DbError::Conflict(
+                "The member already has a pending or active elevation".to_string(),
+            ),
+            _ => DbError::from(e),
+        })?;
+
+        Ok(RoleElevation {
+            id,
+            org_id: input.org_id,
+            user_id: input.user_id,
+            role: input.role,
+            previous_role: None,
+            justification: input.justification,
+            duration_hours: input.duration_hours,
+            status: ElevationStatus::Pending,
+            requested_by: input.requested_by,
+            decided_by: None,
+            decision_note: None,
+            decided_at: None,
+            expires_at: None,
+            ended_at: None,
+            ended_by: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<RoleElevation>> {
+        let sql = format!("SELECT {COLUMNS} FROM role_elevations WHERE id = $1");
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .fetch_optional(&self.read_pool)
+            .await?;
+
+        row.map(|r| Self::parse_elevation(&r)).transpose()
+    }
+
+    async fn list_by_org(
+        &self,
+        org_id: Uuid,
+        params: ListParams,
+    ) -> DbResult<ListResult<RoleElevation>> {
+        let limit = params.limit.unwrap_or(100);
+        let fetch_limit = limit + 1;
+
+        let (comparison, order, should_reverse) =
+            params.sort_order.cursor_query_params(params.direction);
+        let (cursor_clause, limit_idx, order, should_reverse) = if params.cursor.is_some() {
+            (
+                format!("AND ROW(created_at, id) {} ROW($2, $3)", comparison),
+                4,
+                order,
+                should_reverse,
+            )
+        } else {
+            (String::new(), 2, params.sort_order.as_sql(), false)
+        };
+
+        let sql = format!(
+            r#"
+            SELECT {COLUMNS}
+            FROM role_elevations
+            WHERE org_id = $1 {cursor_clause}
+            ORDER BY created_at {order}, id {order}
+            LIMIT ${limit_idx}
+            "#
+        );
+
+        let mut q = sqlx::query(&sql).bind(org_id);
+        if let Some(ref cursor) = params.cursor {
+            q = q.bind(cursor.created_at).bind(cursor.id);
+        }
+        let rows = q.bind(fetch_limit).fetch_all(&self.read_pool).await?;
+
+        let has_more = rows.len() as i64 > limit;
+        let mut items = rows
+            .iter()
+            .take(limit as usize)
+            .map(Self::parse_elevation)
+            .collect::<DbResult<Vec<_>>>()?;
+
+        if should_reverse {
+            items.reverse();
+        }
+
+        let direction = if params.cursor.is_some() {
+            params.direction
+        } else {
+            CursorDirection::Forward
+        };
+        let cursors =
+            PageCursors::from_items(&items, has_more, direction, params.cursor.as_ref(), |e| {
+                cursor_from_row(e.created_at, e.id)
+            });
+
+        Ok(ListResult::new(items, has_more, cursors))
+    }
+
+    async fn approve(
+        &self,
+        id: Uuid,
+        decided_by: Option<Uuid>,
+        note: Option<&str>,
+        previous_role: &str,
+        expires_at: DateTime<Utc>,
+    ) -> DbResult<RoleElevation> {
+        let now = truncate_to_millis(Utc::now());
+        let result = sqlx::query(
+            r#"
+            UPDATE role_elevations
+            SET status = 'active', decided_by = $1, decision_note = $2, decided_at = $3,
+                previous_role = $4, expires_at = $5, updated_at = $3
+            WHERE id = $6 AND status = 'pending'
+            "#,
+        )
+        .bind(decided_by)
+        .bind(note)
+        .bind(now)
+        .bind(previous_role)
+        .bind(truncate_to_millis(expires_at))
+        .bind(id)
+        .execute(&self.write_pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+        self.get_fresh(id).await
+    }
+
+    async fn deny(
+        &self,
+        id: Uuid,
+        decided_by: Option<Uuid>,
+        note: Option<&str>,
+    ) -> DbResult<RoleElevation> {
+        let now = truncate_to_millis(Utc::now());
+        let result = sqlx::query(
+            r#"
+            UPDATE role_elevations
+            SET status = 'denied', decided_by = $1, decision_note = $2, decided_at = $3,
+                updated_at = $3
+            WHERE id = $4 AND status = 'pending'
+            "#,
+        )
+        .bind(decided_by)
+        .bind(note)
+        .bind(now)
+        .bind(id)
+        .execute(&self.write_pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+        self.get_fresh(id).await
+    }
+
+    async fn end(
+        &self,
+        id: Uuid,
+        from: ElevationStatus,
+        to: ElevationStatus,
+        ended_by: Option<Uuid>,
+    ) -> DbResult<bool> {
+        let now = truncate_to_millis(Utc::now());
+        let result = sqlx::query(
+            r#"
+            UPDATE role_elevations
+            SET status = $1, ended_at = $2, ended_by = $3, updated_at = $2
+            WHERE id = $4 AND status = $5
+            "#,
+        )
+        .bind(to.as_str())
+        .bind(now)
+        .bind(ended_by)
+        .bind(id)
+        .bind(from.as_str())
+        .execute(&self.write_pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list_expired(&self, now: DateTime<Utc>, limit: i64) -> DbResult<Vec<RoleElevation>> {
+        let sql = format!(
+            "SELECT {COLUMNS} FROM role_elevations \
+             WHERE status = 'active' AND expires_at <= $1 \
+             ORDER BY expires_at ASC, id ASC \
+             LIMIT $2"
+        );
+        // Read from the primary so a just-ended elevation isn't picked up again
+        let rows = sqlx::query(&sql)
+            .bind(now)
+            .bind(limit)
+            .fetch_all(&self.write_pool)
+            .await?;
+
+        rows.iter().map(Self::parse_elevation).collect()
+    }
+}
@@ -0,0 +1,236 @@
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use sqlx::{PgPool, Row, postgres::PgRow};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{
+            CursorDirection, ListParams, ListResult, PageCursors, ReportRunRepo, cursor_from_row,
+            truncate_to_millis,
+        },
+    },
+    models::{CreateReportRun, ReportRun, ReportRunFilter},
+};
+
+/// Columns selected by list queries. `content` is omitted because rendered
+/// reports can be large and listings never show them.
+const SUMMARY_COLUMNS: &str = "id, report_name, kind, org_id, period_start, period_end, \
+                               format, delivery, status::TEXT AS status, error, created_at";
+
+pub struct PostgresReportRunRepo {
+    write_pool: PgPool,
+    read_pool: PgPool,
+}
+
+impl PostgresReportRunRepo {
+    pub fn new(write_pool: PgPool, read_pool: Option<PgPool>) -> Self {
+        let read_pool = read_pool.unwrap_or_else(|| write_pool.clone());
+        Self {
+            write_pool,
+            read_pool,
+        }
+    }
+
+    fn parse_run(row: &PgRow, with_content: bool) -> DbResult<ReportRun> {
+        Ok(ReportRun {
+            id: row.get("id"),
+            report_name: row.get("report_name"),
+            kind: row
+                .get::<String, _>("kind")
+                .parse()
+                .map_err(DbError::Internal)?,
+            org_id: row.get("org_id"),
+            period_start: row.get("period_start"),
+            period_end: row.get("period_end"),
+            format: row
+                .get::<String, _>("format")
+                .parse()
+                .map_err(DbError::Internal)?,
+            delivery: row.get("delivery"),
+            status: row
+                .get::<String, _>("status")
+                .parse()
+                .map_err(DbError::Internal)?,
+            error: row.get("error"),
+            content: if with_content {
+                row.get("content")
+            } else {
+                None
+            },
+            created_at: row.get("created_at"),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl ReportRunRepo for PostgresReportRunRepo {
+    async fn create(&self, input: CreateReportRun) -> DbResult<ReportRun> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+
+        sqlx::query(
+            r#"
+            INSERT INTO scheduled_report_runs (
+                id, report_name, kind, org_id, period_start, period_end,
+                format, delivery, status, error, content, created_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9::report_run_status, $10, $11, $12)
+            "#,
+        )
+        .bind(id)
+        .bind(&input.report_name)
+        .bind(input.kind.as_str())
+        .bind(input.org_id)
+        .bind(input.period_start)
+        .bind(input.period_end)
+        .bind(input.format.as_str())
+        .bind(&input.delivery)
+        .bind(input.status.as_str())
+        .bind(&input.error)
+        .bind(&input.content)
+        .bind(now)
+        .execute(&self.write_pool)
+        .await?;
+
+        Ok(ReportRun {
+            id,
+            report_name: input.report_name,
+            kind: input.kind,
+            org_id: input.org_id,
+            period_start: input.period_start,
+            period_end: input.period_end,
+            format: input.format,
+            delivery: input.delivery,
+            status: input.status,
+            error: input.error,
+            content: input.content,
+            created_at: now,
+        })
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<ReportRun>> {
+        let sql =
+            format!("SELECT {SUMMARY_COLUMNS}, content FROM scheduled_report_runs WHERE id = $1");
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .fetch_optional(&self.read_pool)
+            .await?;
+
+        row.map(|r| Self::parse_run(&r, true)).transpose()
+    }
+
+    async fn list(
+        &self,
+        filter: ReportRunFilter,
+        params: ListParams,
+    ) -> DbResult<ListResult<ReportRun>> {
+        let limit = params.limit.unwrap_or(100);
+        let fetch_limit = limit + 1;
+
+        let mut conditions = Vec::new();
+        let mut param_idx = 1u32;
+        if filter.org_id.is_some() {
+            conditions.push(format!("org_id = ${}", param_idx));
+            param_idx += 1;
+        }
+        if filter.report_name.is_some() {
+            conditions.push(format!("report_name = ${}", param_idx));
+            param_idx += 1;
+        }
+
+        let (comparison, order, should_reverse) =
+            params.sort_order.cursor_query_params(params.direction);
+        let (order, should_reverse) = if params.cursor.is_some() {
+            conditions.push(format!(
+                "ROW(created_at, id) {} ROW(${}, ${})",
+                comparison,
+                param_idx,
+                param_idx + 1
+            ));
+            param_idx += 2;
+            (order, should_reverse)
+        } else {
+            (params.sort_order.as_sql(), false)
+        };
+
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+
+        let sql = format!(
+            r#"
+            SELECT {SUMMARY_COLUMNS}
+            FROM scheduled_report_runs
+            {where_clause}
+            ORDER BY created_at {order}, id {order}
+            LIMIT ${param_idx}
+            "#
+        );
+
+        let mut q = sqlx::query(&sql);
+        if let Some(org_id) = filter.org_id {
+            q = q.bind(org_id);
+        }
+        if let Some(ref report_name) = filter.report_name {
+            q = q.bind(report_name.clone());
+        }
+        if let Some(ref cursor) = params.cursor {
+            q = q.bind(cursor.created_at).bind(cursor.id);
+        }
+        let rows = q.bind(fetch_limit).fetch_all(&self.read_pool).await?;
+
+        let has_more = rows.len() as i64 > limit;
+        let mut items = rows
+            .iter()
+            .take(limit as usize)
+            .map(|row| Self::parse_run(row, false))
+            .collect::<DbResult<Vec<_>>>()?;
+
+        if should_reverse {
+            items.reverse();
+        }
+
+        let direction = if params.cursor.is_some() {
+            params.direction
+        } else {
+            CursorDirection::Forward
+        };
+        let cursors =
+            PageCursors::from_items(&items, has_more, direction, params.cursor.as_ref(), |run| {
+                cursor_from_row(run.created_at, run.id)
+            });
+
+        Ok(ListResult::new(items, has_more, cursors))
+    }
+
+    async fn list_for_period(
+        &self,
+        report_name: &str,
+        org_id: Uuid,
+        period_start: NaiveDate,
+    ) -> DbResult<Vec<ReportRun>> {
+        let sql = format!(
+            r#"
+            SELECT {SUMMARY_COLUMNS}
+            FROM scheduled_report_runs
+            WHERE report_name = $1 AND org_id = $2 AND period_start = $3
+            ORDER BY created_at ASC, id ASC
+            "#
+        );
+        // Read from the primary: the scheduler calls this right after
+        // recording a run and must not miss it due to replica lag.
+        let rows = sqlx::query(&sql)
+            .bind(report_name)
+            .bind(org_id)
+            .bind(period_start)
+            .fetch_all(&self.write_pool)
+            .await?;
+
+        rows.iter().map(|row| Self::parse_run(row, false)).collect()
+    }
+}
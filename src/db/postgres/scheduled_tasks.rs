@@ -0,0 +1,391 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Row, postgres::PgRow};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{ScheduledTaskRepo, truncate_to_millis},
+    },
+    models::{
+        CreateScheduledTask, CreateScheduledTaskRun, ScheduledTask, ScheduledTaskDelivery,
+        ScheduledTaskProgress, ScheduledTaskRun, UpdateScheduledTask,
+    },
+};
+
+const TASK_COLUMNS: &str = "id, org_id, name, description, schedule, model, instructions, \
+                            prompt, delivery_type, conversation_id, webhook_url, \
+                            webhook_secret, enabled, next_run_at, last_run_at, \
+                            consecutive_failures, created_at, updated_at";
+
+const RUN_COLUMNS: &str = "id, task_id, status, output_text, error, input_tokens, \
+                           output_tokens, cost_microcents, started_at, finished_at";
+
+pub struct PostgresScheduledTaskRepo {
+    write_pool: PgPool,
+    read_pool: PgPool,
+}
+
+impl PostgresScheduledTaskRepo {
+    pub fn new(write_pool: PgPool, read_pool: Option<PgPool>) -> Self {
+        let read_pool = read_pool.unwrap_or_else(|| write_pool.clone());
+        Self {
+            write_pool,
+            read_pool,
+        }
+    }
+
+    fn parse_task(row: &PgRow) -> DbResult<ScheduledTask> {
+        let delivery_type: String = row.get("delivery_type");
+        let delivery = match delivery_type.as_str() {
+            "conversation" => ScheduledTaskDelivery::Conversation {
+                conversation_id: row.get::<Option<Uuid>, _>("conversation_id").ok_or_else(
+                    || DbError::Internal("Scheduled task has no conversation".to_string()),
+                )?,
+            },
+            "webhook" => ScheduledTaskDelivery::Webhook {
+                url: row
+                    .get::<Option<String>, _>("webhook_url")
+                    .unwrap_or_default(),
+                signing_secret: row.get("webhook_secret"),
+            },
+            other => {
+                return Err(DbError::Internal(format!(
+                    "Invalid scheduled task delivery type: {other}"
+                )));
+            }
+        };
+
+        Ok(ScheduledTask {
+            id: row.get("id"),
+            org_id: row.get("org_id"),
+            name: row.get("name"),
+            description: row.get("description"),
+            schedule: row.get("schedule"),
+            model: row.get("model"),
+            instructions: row.get("instructions"),
+            prompt: row.get("prompt"),
+            delivery,
+            enabled: row.get("enabled"),
+            next_run_at: row.get("next_run_at"),
+            last_run_at: row.get("last_run_at"),
+            consecutive_failures: row.get("consecutive_failures"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        })
+    }
+
+    fn parse_run(row: &PgRow) -> DbResult<ScheduledTaskRun> {
+        let status: String = row.get("status");
+        Ok(ScheduledTaskRun {
+            id: row.get("id"),
+            task_id: row.get("task_id"),
+            status: status.parse().map_err(DbError::Internal)?,
+            output_text: row.get("output_text"),
+            error: row.get("error"),
+            input_tokens: row.get("input_tokens"),
+            output_tokens: row.get("output_tokens"),
+            cost_microcents: row.get("cost_microcents"),
+            started_at: row.get("started_at"),
+            finished_at: row.get("finished_at"),
+        })
+    }
+
+    fn map_name_conflict(name: &str) -> impl FnOnce(sqlx::Error) -> DbError + '_ {
+        move |e| match e {
+            sqlx::Error::Database(db_err) if db_err.is_unique_violation() => DbError::Conflict(
+                format!("Scheduled task '{name}' already exists in this organization"),
+            ),
+            _ => DbError::from(e),
+        }
+    }
+}
+
+/// Split a delivery into its `conversation_id`, `webhook_url` and
+/// `webhook_secret` columns.
+fn delivery_columns(
+    delivery: &ScheduledTaskDelivery,
+) -> (Option<Uuid>, Option<String>, Option<String>) {
+    match delivery {
+        ScheduledTaskDelivery::Conversation { conversation_id } => {
+            (Some(*conversation_id), None, None)
+        }
+        ScheduledTaskDelivery::Webhook {
+            url,
+            signing_secret,
+        } => (None, Some(url.clone()), signing_secret.clone()),
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl ScheduledTaskRepo for PostgresScheduledTaskRepo {
+    async fn create(
+        &self,
+        org_id: Uuid,
+        input: CreateScheduledTask,
+        next_run_at: Option<DateTime<Utc>>,
+    ) -> DbResult<ScheduledTask> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+        let next_run_at = next_run_at.map(truncate_to_millis);
+        let (conversation_id, webhook_url, webhook_secret) = delivery_columns(&input.delivery);
+
+        sqlx::query(
+            r#"
+            INSERT INTO scheduled_tasks (
+                id, org_id, name, description, schedule, model, instructions, prompt,
+                delivery_type, conversation_id, webhook_url, webhook_secret, enabled,
+                next_run_at, consecutive_failures, created_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, 0, $15, $15)
+            "#,
+        )
+        .bind(id)
+        .bind(org_id)
+        .bind(&input.name)
+        .bind(&input.description)
+        .bind(&input.schedule)
+        .bind(&input.model)
+        .bind(&input.instructions)
+        .bind(&input.prompt)
+        .bind(input.delivery.as_str())
+        .bind(conversation_id)
+        .bind(&webhook_url)
+        .bind(&webhook_secret)
+        .bind(input.enabled)
+        .bind(next_run_at)
+        .bind(now)
+        .execute(&self.write_pool)
+        .await
+        .map_err(Self::map_name_conflict(&input.name))?;
+
+        Ok(ScheduledTask {
+            id,
+            org_id,
+            name: input.name,
+            description: input.description,
+            schedule: input.schedule,
+            model: input.model,
+            instructions: input.instructions,
+            prompt: input.prompt,
+            delivery: input.delivery,
+            enabled: input.enabled,
+            next_run_at,
+            last_run_at: None,
+            consecutive_failures: 0,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn get(&self, id: Uuid) -> DbResult<Option<ScheduledTask>> {
+        let sql = format!("SELECT {TASK_COLUMNS} FROM scheduled_tasks WHERE id = $1");
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .fetch_optional(&self.read_pool)
+            .await?;
+
+        row.map(|r| Self::parse_task(&r)).transpose()
+    }
+
+    async fn list_by_org(&self, org_id: Uuid) -> DbResult<Vec<ScheduledTask>> {
+        let sql = format!(
+            "SELECT {TASK_COLUMNS} FROM scheduled_tasks WHERE org_id = $1 ORDER BY name ASC"
+        );
+        let rows = sqlx::query(&sql)
+            .bind(org_id)
+            .fetch_all(&self.read_pool)
+            .await?;
+
+        rows.iter().map(Self::parse_task).collect()
+    }
+
+    async fn count_by_org(&self, org_id: Uuid) -> DbResult<i64> {
+        let row = sqlx::query("SELECT COUNT(*) as count FROM scheduled_tasks WHERE org_id = $1")
+            .bind(org_id)
+            .fetch_one(&self.read_pool)
+            .await?;
+
+        Ok(row.get("count"))
+    }
+
+    async fn update(
+        &self,
+        id: Uuid,
+        input: UpdateScheduledTask,
+        next_run_at: Option<DateTime<Utc>>,
+    ) -> DbResult<ScheduledTask> {
+        let mut tx = self.write_pool.begin().await?;
+        let sql = format!("SELECT {TASK_COLUMNS} FROM scheduled_tasks WHERE id = $1 FOR UPDATE");
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or(DbError::NotFound)?;
+        let mut task = Self::parse_task(&row)?;
+        input.apply(&mut task);
+        task.next_run_at = next_run_at.map(truncate_to_millis);
+        task.updated_at = truncate_to_millis(Utc::now());
+        let (conversation_id, webhook_url, webhook_secret) = delivery_columns(&task.delivery);
+
+        sqlx::query(
+            r#"
+            UPDATE scheduled_tasks SET name = $1, description = $2, schedule = $3, model = $4,
+                instructions = $5, prompt = $6, delivery_type = $7, conversation_id = $8,
+                webhook_url = $9, webhook_secret = $10, enabled = $11, next_run_at = $12,
+                consecutive_failures = $13, updated_at = $14
+            WHERE id = $15
+            "#,
+        )
+        .bind(&task.name)
+        .bind(&task.description)
+        .bind(&task.schedule)
+        .bind(&task.model)
+        .bind(&task.instructions)
+        .bind(&task.prompt)
+        .bind(task.delivery.as_str())
+        .bind(conversation_id)
+        .bind(&webhook_url)
+        .bind(&webhook_secret)
+        .bind(task.enabled)
+        .bind(task.next_run_at)
+        .bind(task.consecutive_failures)
+        .bind(task.updated_at)
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(Self::map_name_conflict(&task.name))?;
+        tx.commit().await?;
+
+        Ok(task)
+    }
+
+    async fn delete(&self, id: Uuid) -> DbResult<()> {
+        // Runs go with the task via ON DELETE CASCADE
+        let result = sqlx::query("DELETE FROM scheduled_tasks WHERE id = $1")
+            .bind(id)
+            .execute(&self.write_pool)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+        Ok(())
+    }
+
+    async fn list_due(&self, now: DateTime<Utc>, limit: i64) -> DbResult<Vec<ScheduledTask>> {
+        // Read from the primary: a lagging replica would hand out tasks that
+        // already ran.
+        let sql = format!(
+            "SELECT {TASK_COLUMNS} FROM scheduled_tasks \
+             WHERE enabled AND next_run_at <= $1 \
+             ORDER BY next_run_at ASC, id ASC LIMIT $2"
+        );
+        let rows = sqlx::query(&sql)
+            .bind(now)
+            .bind(limit)
+            .fetch_all(&self.write_pool)
+            .await?;
+
+        rows.iter().map(Self::parse_task).collect()
+    }
+
+    async fn record_run(
+        &self,
+        run: CreateScheduledTaskRun,
+        progress: ScheduledTaskProgress,
+    ) -> DbResult<ScheduledTaskRun> {
+        let started_at = truncate_to_millis(run.started_at);
+        let finished_at = truncate_to_millis(run.finished_at);
+
+        let mut tx = self.write_pool.begin().await?;
+        let result = sqlx::query(
+            r#"
+            UPDATE scheduled_tasks SET next_run_at = $1, last_run_at = $2,
+                consecutive_failures = $3, enabled = $4
+            WHERE id = $5
+            "#,
+        )
+        .bind(progress.next_run_at.map(truncate_to_millis))
+        .bind(finished_at)
+        .bind(progress.consecutive_failures)
+        .bind(progress.enabled)
+        .bind(run.task_id)
+        .execute(&mut *tx)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO scheduled_task_runs (
+                id, task_id, status, output_text, error, input_tokens, output_tokens,
+                cost_microcents, started_at, finished_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            "#,
+        )
+        .bind(run.id)
+        .bind(run.task_id)
+        .bind(run.status.as_str())
+        .bind(&run.output_text)
+        .bind(&run.error)
+        .bind(run.input_tokens)
+        .bind(run.output_tokens)
+        .bind(run.cost_microcents)
+        .bind(started_at)
+        .bind(finished_at)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(ScheduledTaskRun {
+            id: run.id,
+            task_id: run.task_id,
+            status: run.status,
+            output_text: run.output_text,
+            error: run.error,
+            input_tokens: run.input_tokens,
+            output_tokens: run.output_tokens,
+            cost_microcents: run.cost_microcents,
+            started_at,
+            finished_at,
+        })
+    }
+
+    async fn list_runs(&self, task_id: Uuid, limit: i64) -> DbResult<Vec<ScheduledTaskRun>> {
+        let sql = format!(
+            "SELECT {RUN_COLUMNS} FROM scheduled_task_runs WHERE task_id = $1 \
+             ORDER BY started_at DESC, id DESC LIMIT $2"
+        );
+        let rows = sqlx::query(&sql)
+            .bind(task_id)
+            .bind(limit)
+            .fetch_all(&self.read_pool)
+            .await?;
+
+        rows.iter().map(Self::parse_run).collect()
+    }
+
+    async fn prune_runs(&self, task_id: Uuid, keep: i64) -> DbResult<u64> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM scheduled_task_runs
+            WHERE task_id = $1 AND id NOT IN (
+                SELECT id FROM scheduled_task_runs
+                WHERE task_id = $1
+                ORDER BY started_at DESC, id DESC
+                LIMIT $2
+            )
+            "#,
+        )
+        .bind(task_id)
+        .bind(keep)
+        .execute(&self.write_pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
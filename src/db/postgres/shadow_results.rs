@@ -0,0 +1,283 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::{PgPool, Row, postgres::PgRow};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        error::DbResult,
+        repos::{
+            CursorDirection, ListParams, ListResult, PageCursors, ShadowResultRepo,
+            cursor_from_row, truncate_to_millis,
+        },
+    },
+    models::{CreateShadowResult, ShadowReportRow, ShadowResult, ShadowResultFilter},
+};
+
+const COLUMNS: &str = "id, rule, org_id, project_id, primary_provider, primary_model, \
+                       primary_status, primary_latency_ms, primary_input_tokens, \
+                       primary_output_tokens, primary_cost_microcents, primary_output, \
+                       shadow_provider, shadow_model, shadow_status, shadow_latency_ms, \
+                       shadow_input_tokens, shadow_output_tokens, shadow_cost_microcents, \
+                       shadow_output, shadow_error, similarity, created_at";
+
+/// Filter on `ShadowResultFilter`, bound as `$1`-`$4`.
+const FILTER: &str = "($1::TEXT IS NULL OR rule = $1) AND ($2::UUID IS NULL OR org_id = $2) \
+                      AND ($3::TIMESTAMPTZ IS NULL OR created_at >= $3) \
+                      AND ($4::TIMESTAMPTZ IS NULL OR created_at < $4)";
+
+pub struct PostgresShadowResultRepo {
+    write_pool: PgPool,
+    read_pool: PgPool,
+}
+
+impl PostgresShadowResultRepo {
+    pub fn new(write_pool: PgPool, read_pool: Option<PgPool>) -> Self {
+        let read_pool = read_pool.unwrap_or_else(|| write_pool.clone());
+        Self {
+            write_pool,
+            read_pool,
+        }
+    }
+
+    fn parse_result(row: &PgRow) -> ShadowResult {
+        ShadowResult {
+            id: row.get("id"),
+            rule: row.get("rule"),
+            org_id: row.get("org_id"),
+            project_id: row.get("project_id"),
+            primary_provider: row.get("primary_provider"),
+            primary_model: row.get("primary_model"),
+            primary_status: row.get("primary_status"),
+            primary_latency_ms: row.get("primary_latency_ms"),
+            primary_input_tokens: row.get("primary_input_tokens"),
+            primary_output_tokens: row.get("primary_output_tokens"),
+            primary_cost_microcents: row.get("primary_cost_microcents"),
+            primary_output: row.get("primary_output"),
+            shadow_provider: row.get("shadow_provider"),
+            shadow_model: row.get("shadow_model"),
+            shadow_status: row.get("shadow_status"),
+            shadow_latency_ms: row.get("shadow_latency_ms"),
+            shadow_input_tokens: row.get("shadow_input_tokens"),
+            shadow_output_tokens: row.get("shadow_output_tokens"),
+            shadow_cost_microcents: row.get("shadow_cost_microcents"),
+            shadow_output: row.get("shadow_output"),
+            shadow_error: row.get("shadow_error"),
+            similarity: row.get("similarity"),
+            created_at: row.get("created_at"),
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl ShadowResultRepo for PostgresShadowResultRepo {
+    async fn create(&self, input: CreateShadowResult) -> DbResult<ShadowResult> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+
+        let sql = format!(
+            "INSERT INTO shadow_results ({COLUMNS}) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, \
+             $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23)"
+        );
+        sqlx::query(&sql)
+            .bind(id)
+            .bind(&input.rule)
+            .bind(input.org_id)
+            .bind(input.project_id)
+            .bind(&input.primary_provider)
+            .bind(&input.primary_model)
+            .bind(input.primary_status)
+            .bind(input.primary_latency_ms)
+            .bind(input.primary_input_tokens)
+            .bind(input.primary_output_tokens)
+            .bind(input.primary_cost_microcents)
+            .bind(&input.primary_output)
+            .bind(&input.shadow_provider)
+            .bind(&input.shadow_model)
+            .bind(input.shadow_status)
+            .bind(input.shadow_latency_ms)
+            .bind(input.shadow_input_tokens)
+            .bind(input.shadow_output_tokens)
+            .bind(input.shadow_cost_microcents)
+            .bind(&input.shadow_output)
+            .bind(&input.shadow_error)
+            .bind(input.similarity)
+            .bind(now)
+            .execute(&self.write_pool)
+            .await?;
+
+        Ok(ShadowResult {
+            id,
+            rule: input.rule,
+            org_id: input.org_id,
+            project_id: input.project_id,
+            primary_provider: input.primary_provider,
+            primary_model: input.primary_model,
+            primary_status: input.primary_status,
+            primary_latency_ms: input.primary_latency_ms,
+            primary_input_tokens: input.primary_input_tokens,
+            primary_output_tokens: input.primary_output_tokens,
+            primary_cost_microcents: input.primary_cost_microcents,
+            primary_output: input.primary_output,
+            shadow_provider: input.shadow_provider,
+            shadow_model: input.shadow_model,
+            shadow_status: input.shadow_status,
+            shadow_latency_ms: input.shadow_latency_ms,
+            shadow_input_tokens: input.shadow_input_tokens,
+            shadow_output_tokens: input.shadow_output_tokens,
+            shadow_cost_microcents: input.shadow_cost_microcents,
+            shadow_output: input.shadow_output,
+            shadow_error: input.shadow_error,
+            similarity: input.similarity,
+            created_at: now,
+        })
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<ShadowResult>> {
+        let sql = format!("SELECT {COLUMNS} FROM shadow_results WHERE id = $1");
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .fetch_optional(&self.read_pool)
+            .await?;
+
+        Ok(row.map(|r| Self::parse_result(&r)))
+    }
+
+    async fn list(
+        &self,
+        filter: &ShadowResultFilter,
+        params: ListParams,
+    ) -> DbResult<ListResult<ShadowResult>> {
+        let limit = params.limit.unwrap_or(100);
+        let fetch_limit = limit + 1;
+
+        let (comparison, order, should_reverse) =
+            params.sort_order.cursor_query_params(params.direction);
+        let (cursor_clause, limit_param, order, should_reverse) = if params.cursor.is_some() {
+            (
+                format!("AND ROW(created_at, id) {} ROW($5, $6)", comparison),
+                "$7",
+                order,
+                should_reverse,
+            )
+        } else {
+            (String::new(), "$5", params.sort_order.as_sql(), false)
+        };
+
+        let sql = format!(
+            r#"
+            SELECT {COLUMNS}
+            FROM shadow_results
+            WHERE {FILTER} {cursor_clause}
+            ORDER BY created_at {order}, id {order}
+            LIMIT {limit_param}
+            "#
+        );
+
+        let mut q = sqlx::query(&sql)
+            .bind(&filter.rule)
+            .bind(filter.org_id)
+            .bind(filter.from)
+            .bind(filter.to);
+        if let Some(ref cursor) = params.cursor {
+            q = q.bind(cursor.created_at).bind(cursor.id);
+        }
+        let rows = q.bind(fetch_limit).fetch_all(&self.read_pool).await?;
+
+        let has_more = rows.len() as i64 > limit;
+        let mut items: Vec<ShadowResult> = rows
+            .iter()
+            .take(limit as usize)
+            .map(Self::parse_result)
+            .collect();
+
+        if should_reverse {
+            items.reverse();
+        }
+
+        let direction = if params.cursor.is_some() {
+            params.direction
+        } else {
+            CursorDirection::Forward
+        };
+        let cursors = PageCursors::from_items(
+            &items,
+            has_more,
+            direction,
+            params.cursor.as_ref(),
+            |result| cursor_from_row(result.created_at, result.id),
+        );
+
+        Ok(ListResult::new(items, has_more, cursors))
+    }
+
+    async fn report(&self, filter: &ShadowResultFilter) -> DbResult<Vec<ShadowReportRow>> {
+        let sql = format!(
+            r#"
+            SELECT rule, primary_provider, primary_model, shadow_provider, shadow_model,
+                   COUNT(*) AS samples,
+                   COUNT(*) FILTER (WHERE primary_status NOT BETWEEN 200 AND 299)
+                       AS primary_errors,
+                   COUNT(*) FILTER (WHERE shadow_status IS NULL
+                                       OR shadow_status NOT BETWEEN 200 AND 299)
+                       AS shadow_errors,
+                   COUNT(*) FILTER (WHERE similarity >= 1.0) AS identical,
+                   AVG(similarity) AS avg_similarity,
+                   AVG(primary_latency_ms)::FLOAT8 AS primary_avg_latency_ms,
+                   AVG(shadow_latency_ms)::FLOAT8 AS shadow_avg_latency_ms,
+                   COALESCE(SUM(primary_output_tokens), 0)::BIGINT AS primary_output_tokens,
+                   COALESCE(SUM(shadow_output_tokens), 0)::BIGINT AS shadow_output_tokens,
+                   COALESCE(SUM(primary_cost_microcents), 0)::BIGINT AS primary_cost_microcents,
+                   COALESCE(SUM(shadow_cost_microcents), 0)::BIGINT AS shadow_cost_microcents
+            FROM shadow_results
+            WHERE {FILTER}
+            GROUP BY rule, primary_provider, primary_model, shadow_provider, shadow_model
+            ORDER BY rule, samples DESC
+            "#
+        );
+
+        let rows = sqlx::query(&sql)
+            .bind(&filter.rule)
+            .bind(filter.org_id)
+            .bind(filter.from)
+            .bind(filter.to)
+            .fetch_all(&self.read_pool)
+            .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| ShadowReportRow {
+                rule: row.get("rule"),
+                primary_provider: row.get("primary_provider"),
+                primary_model: row.get("primary_model"),
+                shadow_provider: row.get("shadow_provider"),
+                shadow_model: row.get("shadow_model"),
+                samples: row.get("samples"),
+                primary_errors: row.get("primary_errors"),
+                shadow_errors: row.get("shadow_errors"),
+                identical: row.get("identical"),
+                avg_similarity: row.get("avg_similarity"),
+                primary_avg_latency_ms: row.get("primary_avg_latency_ms"),
+                shadow_avg_latency_ms: row.get("shadow_avg_latency_ms"),
+                primary_output_tokens: row.get("primary_output_tokens"),
+                shadow_output_tokens: row.get("shadow_output_tokens"),
+                primary_cost_microcents: row.get("primary_cost_microcents"),
+                shadow_cost_microcents: row.get("shadow_cost_microcents"),
+            })
+            .collect())
+    }
+
+    async fn delete(&self, filter: &ShadowResultFilter) -> DbResult<u64> {
+        let sql = format!("DELETE FROM shadow_results WHERE {FILTER}");
+        let result = sqlx::query(&sql)
+            .bind(&filter.rule)
+            .bind(filter.org_id)
+            .bind(filter.from)
+            .bind(filter.to)
+            .execute(&self.write_pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
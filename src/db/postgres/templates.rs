@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use async_trait::async_trait;
 use sqlx::{PgPool, Row};
@@ -50,6 +50,8 @@ impl PostgresTemplateRepo {
             description: row.get("description"),
             content: row.get("content"),
             metadata,
+            labels: serde_json::from_value(row.get("labels"))
+                .map_err(|e| DbError::Internal(format!("failed to deserialize labels: {e}")))?,
             created_at: row.get("created_at"),
             updated_at: row.get("updated_at"),
         })
@@ -76,12 +78,13 @@ impl PostgresTemplateRepo {
 
         let query = format!(
             r#"
-            SELECT id, owner_type::TEXT, owner_id, name, description, content, metadata, created_at, updated_at
+            SELECT id, owner_type::TEXT, owner_id, name, description, content, metadata, labels, created_at, updated_at
             FROM templates
             WHERE owner_type = $1 AND owner_id = $2 AND ROW(created_at, id) {} ROW($3, $4)
             {}
+            AND labels @> $5
             ORDER BY created_at {}, id {}
-            LIMIT $5
+            LIMIT $6
             "#,
             comparison, deleted_filter, order, order
         );
@@ -91,6 +94,7 @@ impl PostgresTemplateRepo {
             .bind(owner_id)
             .bind(cursor.created_at)
             .bind(cursor.id)
+            .bind(params.labels.to_json())
             .bind(fetch_limit)
             .fetch_all(&self.read_pool)
             .await?;
@@ -134,7 +138,7 @@ impl TemplateRepo for PostgresTemplateRepo {
             r#"
             INSERT INTO templates (id, owner_type, owner_id, name, description, content, metadata)
             VALUES ($1, $2, $3, $4, $5, $6, $7)
-            RETURNING id, owner_type::TEXT, owner_id, name, description, content, metadata, created_at, updated_at
+            RETURNING id, owner_type::TEXT, owner_id, name, description, content, metadata, labels, created_at, updated_at
             "#,
         )
         .bind(id)
@@ -162,7 +166,7 @@ impl TemplateRepo for PostgresTemplateRepo {
     async fn get_by_id(&self, id: Uuid) -> DbResult<Option<Template>> {
         let result = sqlx::query(
             r#"
-            SELECT id, owner_type::TEXT, owner_id, name, description, content, metadata, created_at, updated_at
+            SELECT id, owner_type::TEXT, owner_id, name, description, content, metadata, labels, created_at, updated_at
             FROM templates
             WHERE id = $1 AND deleted_at IS NULL
             "#,
@@ -180,7 +184,7 @@ impl TemplateRepo for PostgresTemplateRepo {
     async fn get_by_id_and_org(&self, id: Uuid, org_id: Uuid) -> DbResult<Option<Template>> {
         let result = sqlx::query(
             r#"
-            SELECT p.id, p.owner_type::TEXT, p.owner_id, p.name, p.description, p.content, p.metadata, p.created_at, p.updated_at
+            SELECT p.id, p.owner_type::TEXT, p.owner_id, p.name, p.description, p.content, p.metadata, p.labels, p.created_at, p.updated_at
             FROM templates p
             WHERE p.id = $1 AND p.deleted_at IS NULL
             AND (
@@ -231,25 +235,28 @@ impl TemplateRepo for PostgresTemplateRepo {
 
         let query = if params.include_deleted {
             r#"
-            SELECT id, owner_type::TEXT, owner_id, name, description, content, metadata, created_at, updated_at
+            SELECT id, owner_type::TEXT, owner_id, name, description, content, metadata, labels, created_at, updated_at
             FROM templates
             WHERE owner_type = $1 AND owner_id = $2
+            AND labels @> $3
             ORDER BY created_at DESC, id DESC
-            LIMIT $3
+            LIMIT $4
             "#
         } else {
             r#"
-            SELECT id, owner_type::TEXT, owner_id, name, description, content, metadata, created_at, updated_at
+            SELECT id, owner_type::TEXT, owner_id, name, description, content, metadata, labels, created_at, updated_at
             FROM templates
             WHERE owner_type = $1 AND owner_id = $2 AND deleted_at IS NULL
+            AND labels @> $3
             ORDER BY created_at DESC, id DESC
-            LIMIT $3
+            LIMIT $4
             "#
         };
 
         let rows = sqlx::query(query)
             .bind(owner_type.as_str())
             .bind(owner_id)
+            .bind(params.labels.to_json())
             .bind(fetch_limit)
             .fetch_all(&self.read_pool)
             .await?;
@@ -299,12 +306,13 @@ impl TemplateRepo for PostgresTemplateRepo {
 
             let sql = format!(
                 r#"
-                SELECT p.id, p.owner_type::TEXT, p.owner_id, p.name, p.description, p.content, p.metadata, p.created_at, p.updated_at
+                SELECT p.id, p.owner_type::TEXT, p.owner_id, p.name, p.description, p.content, p.metadata, p.labels, p.created_at, p.updated_at
                 FROM templates p
                 WHERE p.deleted_at IS NULL AND ROW(p.created_at, p.id) {} ROW($2, $3)
                 {}
+                AND p.labels @> $4
                 ORDER BY p.created_at {}, p.id {}
-                LIMIT $4
+                LIMIT $5
                 "#,
                 comparison, org_filter, order, order
             );
@@ -313,6 +321,7 @@ impl TemplateRepo for PostgresTemplateRepo {
                 .bind(org_id)
                 .bind(cursor.created_at)
                 .bind(cursor.id)
+                .bind(params.labels.to_json())
                 .bind(fetch_limit)
                 .fetch_all(&self.read_pool)
                 .await?;
@@ -338,18 +347,20 @@ impl TemplateRepo for PostgresTemplateRepo {
 
         let sql = format!(
             r#"
-            SELECT p.id, p.owner_type::TEXT, p.owner_id, p.name, p.description, p.content, p.metadata, p.created_at, p.updated_at
+            SELECT p.id, p.owner_type::TEXT, p.owner_id, p.name, p.description, p.content, p.metadata, p.labels, p.created_at, p.updated_at
             FROM templates p
             WHERE p.deleted_at IS NULL
             {}
+            AND p.labels @> $2
             ORDER BY p.created_at DESC, p.id DESC
-            LIMIT $2
+            LIMIT $3
             "#,
             org_filter
         );
 
         let rows = sqlx::query(&sql)
             .bind(org_id)
+            .bind(params.labels.to_json())
             .bind(fetch_limit)
             .fetch_all(&self.read_pool)
             .await?;
@@ -425,7 +436,7 @@ impl TemplateRepo for PostgresTemplateRepo {
             UPDATE templates
             SET {}
             WHERE id = ${} AND deleted_at IS NULL
-            RETURNING id, owner_type::TEXT, owner_id, name, description, content, metadata, created_at, updated_at
+            RETURNING id, owner_type::TEXT, owner_id, name, description, content, metadata, labels, created_at, updated_at
             "#,
             set_clauses.join(", "),
             param_idx
@@ -463,6 +474,27 @@ impl TemplateRepo for PostgresTemplateRepo {
         Self::parse_template(&row)
     }
 
+    async fn set_labels(&self, id: Uuid, labels: &BTreeMap<String, String>) -> DbResult<Template> {
+        let labels = serde_json::to_value(labels)
+            .map_err(|e| DbError::Internal(format!("failed to serialize labels: {e}")))?;
+
+        let row = sqlx::query(
+            r#"
+            UPDATE templates
+            SET labels = $1, updated_at = NOW()
+            WHERE id = $2 AND deleted_at IS NULL
+            RETURNING id, owner_type::TEXT, owner_id, name, description, content, metadata, labels, created_at, updated_at
+            "#,
+        )
+        .bind(labels)
+        .bind(id)
+        .fetch_optional(&self.write_pool)
+        .await?
+        .ok_or(DbError::NotFound)?;
+
+        Self::parse_template(&row)
+    }
+
     async fn delete(&self, id: Uuid) -> DbResult<()> {
         let result = sqlx::query(
             r#"
@@ -13,9 +13,10 @@ use crate::{
     },
     models::{
         DailyModelSpend, DailyOrgSpend, DailyPricingSourceSpend, DailyProjectSpend,
-        DailyProviderSpend, DailySpend, DailyTeamSpend, DailyUserSpend, ModelSpend, OrgSpend,
-        PricingSourceSpend, ProjectSpend, ProviderSpend, RefererSpend, TeamSpend, UsageLogEntry,
-        UsageLogRecord, UsageSummary, UserSpend,
+        DailyProviderAttempts, DailyProviderSpend, DailyReliability, DailySpend, DailyTagSpend,
+        DailyTeamSpend, DailyUserSpend, HourlySpend, ModelSpend, OrgSpend, PricingSourceSpend,
+        ProjectSpend, ProviderSpend, RELIABILITY_LATENCY_BUCKETS_MS, RefererSpend, TagSpend,
+        TeamSpend, UsageLogEntry, UsageLogRecord, UsageMix, UsageSummary, UserSpend,
     },
 };
 
@@ -67,9 +68,13 @@ impl UsageRepo for PostgresUsageRepo {
                 image_count, audio_seconds, character_count, provider_source,
                 record_type, tool_name, tool_query, tool_url,
                 tool_bytes_fetched, tool_results_count, tool_runtime_seconds,
-                tool_exit_code
+                tool_exit_code, jwt_subject, error_code,
+                structured_output_repairs, context_original_tokens, context_compressed_tokens,
+                degraded_from_model, degradation_reason, ttft_ms,
+                inter_token_latency_ms, output_tokens_per_second, idempotency_key,
+                provider_attempts, suspected_duplicate_attempts, tags
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26, $27, $28, $29, $30, $31, $32, $33, $34, $35, $36)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26, $27, $28, $29, $30, $31, $32, $33, $34, $35, $36, $37, $38, $39, $40, $41, $42, $43, $44, $45, $46, $47, $48, $49, $50)
             ON CONFLICT (request_id) DO NOTHING
             "#,
         )
@@ -109,6 +114,20 @@ impl UsageRepo for PostgresUsageRepo {
         .bind(entry.tool_results_count)
         .bind(entry.tool_runtime_seconds)
         .bind(entry.tool_exit_code)
+        .bind(&entry.jwt_subject)
+        .bind(&entry.error_code)
+        .bind(entry.structured_output_repairs)
+        .bind(entry.context_original_tokens)
+        .bind(entry.context_compressed_tokens)
+        .bind(&entry.degraded_from_model)
+        .bind(&entry.degradation_reason)
+        .bind(entry.ttft_ms)
+        .bind(entry.inter_token_latency_ms)
+        .bind(entry.output_tokens_per_second)
+        .bind(&entry.idempotency_key)
+        .bind(entry.provider_attempts)
+        .bind(entry.suspected_duplicate_attempts)
+        .bind(entry.tags.as_ref().and_then(|t| serde_json::to_value(t).ok()))
         .execute(&self.write_pool)
         .await?;
 
@@ -121,7 +140,7 @@ impl UsageRepo for PostgresUsageRepo {
         }
 
         // PostgreSQL allows up to 65535 parameters per query
-        // Each entry uses 36 parameters, so we can insert ~1820 entries per batch
+        // Each entry uses 50 parameters, so we can insert ~1310 entries per batch
         // Use 1000 as a reasonable batch size for performance
         const MAX_ENTRIES_PER_BATCH: usize = 1000;
 
@@ -139,15 +158,18 @@ impl UsageRepo for PostgresUsageRepo {
                 .iter()
                 .enumerate()
                 .map(|(i, _)| {
-                    let o = i * 36;
+                    let o = i * 50;
                     format!(
-                        "(${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${})",
+                        "(${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${})",
                         o + 1, o + 2, o + 3, o + 4, o + 5, o + 6,
                         o + 7, o + 8, o + 9, o + 10, o + 11, o + 12,
                         o + 13, o + 14, o + 15, o + 16, o + 17, o + 18,
                         o + 19, o + 20, o + 21, o + 22, o + 23, o + 24,
                         o + 25, o + 26, o + 27, o + 28, o + 29, o + 30,
-                        o + 31, o + 32, o + 33, o + 34, o + 35, o + 36
+                        o + 31, o + 32, o + 33, o + 34, o + 35, o + 36,
+                        o + 37, o + 38, o + 39, o + 40, o + 41, o + 42,
+                        o + 43, o + 44, o + 45, o + 46, o + 47, o + 48,
+                        o + 49, o + 50
                     )
                 })
                 .collect();
@@ -163,7 +185,11 @@ impl UsageRepo for PostgresUsageRepo {
                     image_count, audio_seconds, character_count, provider_source,
                     record_type, tool_name, tool_query, tool_url,
                     tool_bytes_fetched, tool_results_count, tool_runtime_seconds,
-                    tool_exit_code
+                    tool_exit_code, jwt_subject, error_code,
+                    structured_output_repairs, context_original_tokens, context_compressed_tokens,
+                    degraded_from_model, degradation_reason, ttft_ms,
+                    inter_token_latency_ms, output_tokens_per_second, idempotency_key,
+                    provider_attempts, suspected_duplicate_attempts, tags
                 )
                 VALUES {}
                 ON CONFLICT (request_id) DO NOTHING
@@ -213,7 +239,26 @@ impl UsageRepo for PostgresUsageRepo {
                     .bind(entry.tool_bytes_fetched)
                     .bind(entry.tool_results_count)
                     .bind(entry.tool_runtime_seconds)
-                    .bind(entry.tool_exit_code);
+                    .bind(entry.tool_exit_code)
+                    .bind(&entry.jwt_subject)
+                    .bind(&entry.error_code)
+                    .bind(entry.structured_output_repairs)
+                    .bind(entry.context_original_tokens)
+                    .bind(entry.context_compressed_tokens)
+                    .bind(&entry.degraded_from_model)
+                    .bind(&entry.degradation_reason)
+                    .bind(entry.ttft_ms)
+                    .bind(entry.inter_token_latency_ms)
+                    .bind(entry.output_tokens_per_second)
+                    .bind(&entry.idempotency_key)
+                    .bind(entry.provider_attempts)
+                    .bind(entry.suspected_duplicate_attempts)
+                    .bind(
+                        entry
+                            .tags
+                            .as_ref()
+                            .and_then(|t| serde_json::to_value(t).ok()),
+                    );
             }
 
             let result = query_builder.execute(&mut *tx).await?;
@@ -3783,6 +3828,280 @@ impl UsageRepo for PostgresUsageRepo {
             .collect())
     }
 
+    // ==================== Anomaly Detection Queries ====================
+
+    async fn get_hourly_spend(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> DbResult<Vec<HourlySpend>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT api_key_id, org_id,
+                date_trunc('hour', recorded_at AT TIME ZONE 'UTC') AT TIME ZONE 'UTC' as hour,
+                COALESCE(SUM(cost_microcents), 0)::BIGINT as total_cost_microcents,
+                COUNT(*)::BIGINT as request_count
+            FROM usage_records
+            WHERE recorded_at >= $1 AND recorded_at < $2
+            GROUP BY api_key_id, org_id, hour
+            ORDER BY hour ASC
+            "#,
+        )
+        .bind(since)
+        .bind(until)
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| HourlySpend {
+                api_key_id: row.get("api_key_id"),
+                org_id: row.get("org_id"),
+                hour: row.get("hour"),
+                total_cost_microcents: row.get("total_cost_microcents"),
+                request_count: row.get("request_count"),
+            })
+            .collect())
+    }
+
+    async fn get_usage_mix(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> DbResult<Vec<UsageMix>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT api_key_id, org_id, model, http_referer as referer,
+                COALESCE(SUM(cost_microcents), 0)::BIGINT as total_cost_microcents,
+                COUNT(*)::BIGINT as request_count
+            FROM usage_records
+            WHERE recorded_at >= $1 AND recorded_at < $2
+                AND record_type = 'model'
+            GROUP BY api_key_id, org_id, model, http_referer
+            "#,
+        )
+        .bind(since)
+        .bind(until)
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| UsageMix {
+                api_key_id: row.get("api_key_id"),
+                org_id: row.get("org_id"),
+                model: row.get("model"),
+                referer: row.get("referer"),
+                total_cost_microcents: row.get("total_cost_microcents"),
+                request_count: row.get("request_count"),
+            })
+            .collect())
+    }
+
+    // ==================== Reconciliation Queries ====================
+
+    async fn get_daily_provider_attempts(
+        &self,
+        range: DateRange,
+    ) -> DbResult<Vec<DailyProviderAttempts>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT recorded_at::DATE as date, provider, model,
+                COUNT(*)::BIGINT as request_count,
+                COALESCE(SUM(provider_attempts), 0)::BIGINT as provider_attempts,
+                COALESCE(SUM(suspected_duplicate_attempts), 0)::BIGINT as suspected_duplicate_attempts,
+                COALESCE(SUM(input_tokens), 0)::BIGINT as input_tokens,
+                COALESCE(SUM(output_tokens), 0)::BIGINT as output_tokens,
+                COALESCE(SUM(cost_microcents), 0)::BIGINT as cost_microcents
+            FROM usage_records
+            WHERE recorded_at >= $1::DATE AND recorded_at < ($2::DATE + INTERVAL '1 day')
+                AND record_type = 'model'
+            GROUP BY recorded_at::DATE, provider, model
+            ORDER BY recorded_at::DATE ASC, provider ASC, model ASC
+            "#,
+        )
+        .bind(range.start)
+        .bind(range.end)
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| DailyProviderAttempts {
+                date: row.get("date"),
+                provider: row.get("provider"),
+                model: row.get("model"),
+                request_count: row.get("request_count"),
+                provider_attempts: row.get("provider_attempts"),
+                suspected_duplicate_attempts: row.get("suspected_duplicate_attempts"),
+                input_tokens: row.get("input_tokens"),
+                output_tokens: row.get("output_tokens"),
+                cost_microcents: row.get("cost_microcents"),
+            })
+            .collect())
+    }
+
+    // ==================== Tag Queries ====================
+
+    async fn get_tag_usage(
+        &self,
+        key: &str,
+        range: DateRange,
+        org_id: Option<Uuid>,
+        project_id: Option<Uuid>,
+    ) -> DbResult<Vec<TagSpend>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                tags->>$1 as tag_value,
+                COALESCE(SUM(cost_microcents), 0)::BIGINT as total_cost_microcents,
+                COALESCE(SUM(input_tokens), 0)::BIGINT as input_tokens,
+                COALESCE(SUM(output_tokens), 0)::BIGINT as output_tokens,
+                COALESCE(SUM(total_tokens), 0)::BIGINT as total_tokens,
+                COUNT(*)::BIGINT as request_count
+            FROM usage_records
+            WHERE recorded_at >= $2::DATE AND recorded_at < ($3::DATE + INTERVAL '1 day')
+                AND ($4::UUID IS NULL OR org_id = $4)
+                AND ($5::UUID IS NULL OR project_id = $5)
+            GROUP BY tag_value
+            ORDER BY total_cost_microcents DESC
+            "#,
+        )
+        .bind(key)
+        .bind(range.start)
+        .bind(range.end)
+        .bind(org_id)
+        .bind(project_id)
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| TagSpend {
+                tag_value: row.get("tag_value"),
+                total_cost_microcents: row.get("total_cost_microcents"),
+                input_tokens: row.get("input_tokens"),
+                output_tokens: row.get("output_tokens"),
+                total_tokens: row.get("total_tokens"),
+                request_count: row.get("request_count"),
+            })
+            .collect())
+    }
+
+    async fn get_daily_tag_usage(
+        &self,
+        key: &str,
+        range: DateRange,
+        org_id: Option<Uuid>,
+        project_id: Option<Uuid>,
+    ) -> DbResult<Vec<DailyTagSpend>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                recorded_at::DATE as date,
+                tags->>$1 as tag_value,
+                COALESCE(SUM(cost_microcents), 0)::BIGINT as total_cost_microcents,
+                COALESCE(SUM(input_tokens), 0)::BIGINT as input_tokens,
+                COALESCE(SUM(output_tokens), 0)::BIGINT as output_tokens,
+                COALESCE(SUM(total_tokens), 0)::BIGINT as total_tokens,
+                COUNT(*)::BIGINT as request_count
+            FROM usage_records
+            WHERE recorded_at >= $2::DATE AND recorded_at < ($3::DATE + INTERVAL '1 day')
+                AND ($4::UUID IS NULL OR org_id = $4)
+                AND ($5::UUID IS NULL OR project_id = $5)
+            GROUP BY recorded_at::DATE, tag_value
+            ORDER BY recorded_at::DATE ASC, total_cost_microcents DESC
+            "#,
+        )
+        .bind(key)
+        .bind(range.start)
+        .bind(range.end)
+        .bind(org_id)
+        .bind(project_id)
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| DailyTagSpend {
+                date: row.get("date"),
+                tag_value: row.get("tag_value"),
+                total_cost_microcents: row.get("total_cost_microcents"),
+                input_tokens: row.get("input_tokens"),
+                output_tokens: row.get("output_tokens"),
+                total_tokens: row.get("total_tokens"),
+                request_count: row.get("request_count"),
+            })
+            .collect())
+    }
+
+    // ==================== Reliability Queries ====================
+
+    async fn get_daily_reliability(
+        &self,
+        range: DateRange,
+        org_id: Option<Uuid>,
+        project_id: Option<Uuid>,
+    ) -> DbResult<Vec<DailyReliability>> {
+        let latency_buckets = RELIABILITY_LATENCY_BUCKETS_MS
+            .iter()
+            .enumerate()
+            .map(|(i, bound)| {
+                format!("COUNT(*) FILTER (WHERE u.latency_ms <= {bound})::BIGINT as latency_le_{i}")
+            })
+            .collect::<Vec<_>>()
+            .join(",\n                ");
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT u.recorded_at::DATE as date, u.provider, u.model,
+                u.project_id, projects.name as project_name,
+                COUNT(*)::BIGINT as request_count,
+                COUNT(u.error_code)::BIGINT as error_count,
+                COUNT(*) FILTER (WHERE u.error_code IN ('provider_error', 'provider_unavailable', 'timeout'))::BIGINT as provider_errors,
+                COUNT(*) FILTER (WHERE u.error_code = 'content_filtered')::BIGINT as guardrail_blocks,
+                COUNT(*) FILTER (WHERE u.error_code = 'rate_limited')::BIGINT as rate_limited,
+                COUNT(*) FILTER (WHERE u.cached_tokens > 0)::BIGINT as cache_hits,
+                COUNT(u.latency_ms)::BIGINT as latency_count,
+                {latency_buckets}
+            FROM usage_records u
+            LEFT JOIN projects ON u.project_id = projects.id
+            WHERE u.recorded_at >= $1::DATE AND u.recorded_at < ($2::DATE + INTERVAL '1 day')
+                AND u.record_type = 'model'
+                AND ($3::UUID IS NULL OR u.org_id = $3)
+                AND ($4::UUID IS NULL OR u.project_id = $4)
+            GROUP BY u.recorded_at::DATE, u.provider, u.model, u.project_id, projects.name
+            ORDER BY u.recorded_at::DATE ASC, u.provider ASC, u.model ASC
+            "#,
+        ))
+        .bind(range.start)
+        .bind(range.end)
+        .bind(org_id)
+        .bind(project_id)
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| DailyReliability {
+                date: row.get("date"),
+                provider: row.get("provider"),
+                model: row.get("model"),
+                project_id: row.get("project_id"),
+                project_name: row.get("project_name"),
+                request_count: row.get("request_count"),
+                error_count: row.get("error_count"),
+                provider_errors: row.get("provider_errors"),
+                guardrail_blocks: row.get("guardrail_blocks"),
+                rate_limited: row.get("rate_limited"),
+                cache_hits: row.get("cache_hits"),
+                latency_count: row.get("latency_count"),
+                latency_buckets: (0..RELIABILITY_LATENCY_BUCKETS_MS.len())
+                    .map(|i| row.get(format!("latency_le_{i}").as_str()))
+                    .collect(),
+            })
+            .collect())
+    }
+
     // ==================== Individual Log Queries ====================
 
     async fn list_logs(&self, query: UsageLogQuery) -> DbResult<ListResult<UsageLogRecord>> {
@@ -3844,6 +4163,10 @@ impl UsageRepo for PostgresUsageRepo {
             conditions.push(format!("record_type = ${}", param_idx));
             param_idx += 1;
         }
+        if query.error_code.is_some() {
+            conditions.push(format!("error_code = ${}", param_idx));
+            param_idx += 1;
+        }
         if query.from.is_some() {
             conditions.push(format!("recorded_at >= ${}", param_idx));
             param_idx += 1;
@@ -3884,7 +4207,11 @@ impl UsageRepo for PostgresUsageRepo {
                    image_count, audio_seconds, character_count, provider_source,
                    record_type, tool_name, tool_query, tool_url,
                    tool_bytes_fetched, tool_results_count, tool_runtime_seconds,
-                   tool_exit_code
+                   tool_exit_code, jwt_subject, error_code,
+                   structured_output_repairs, context_original_tokens, context_compressed_tokens,
+                   degraded_from_model, degradation_reason, ttft_ms,
+                   inter_token_latency_ms, output_tokens_per_second, idempotency_key,
+                   provider_attempts, suspected_duplicate_attempts, tags
             FROM usage_records
             {}
             ORDER BY recorded_at {}, id {}
@@ -3925,6 +4252,9 @@ impl UsageRepo for PostgresUsageRepo {
         if let Some(record_type) = &query.record_type {
             qb = qb.bind(record_type);
         }
+        if let Some(error_code) = &query.error_code {
+            qb = qb.bind(error_code);
+        }
         if let Some(from) = &query.from {
             qb = qb.bind(from);
         }
@@ -3980,6 +4310,22 @@ impl UsageRepo for PostgresUsageRepo {
                 tool_results_count: row.get("tool_results_count"),
                 tool_runtime_seconds: row.get("tool_runtime_seconds"),
                 tool_exit_code: row.get("tool_exit_code"),
+                jwt_subject: row.get("jwt_subject"),
+                error_code: row.get("error_code"),
+                structured_output_repairs: row.get("structured_output_repairs"),
+                context_original_tokens: row.get("context_original_tokens"),
+                context_compressed_tokens: row.get("context_compressed_tokens"),
+                degraded_from_model: row.get("degraded_from_model"),
+                degradation_reason: row.get("degradation_reason"),
+                ttft_ms: row.get("ttft_ms"),
+                inter_token_latency_ms: row.get("inter_token_latency_ms"),
+                output_tokens_per_second: row.get("output_tokens_per_second"),
+                idempotency_key: row.get("idempotency_key"),
+                provider_attempts: row.get("provider_attempts"),
+                suspected_duplicate_attempts: row.get("suspected_duplicate_attempts"),
+                tags: row
+                    .get::<Option<serde_json::Value>, _>("tags")
+                    .and_then(|v| serde_json::from_value(v).ok()),
             })
             .collect();
 
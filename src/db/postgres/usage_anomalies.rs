@@ -0,0 +1,236 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Row, postgres::PgRow};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{
+            CursorDirection, ListParams, ListResult, PageCursors, UsageAnomalyRepo,
+            cursor_from_row, truncate_to_millis,
+        },
+    },
+    models::{CreateUsageAnomaly, UsageAnomaly, UsageAnomalyFilter},
+};
+
+const COLUMNS: &str = "id, kind, org_id, api_key_id, detail, summary, observed, expected, \
+                       score, window_start, window_end, rate_limited_until, acknowledged_at, \
+                       acknowledged_by, created_at";
+
+/// Filter on `UsageAnomalyFilter`, bound as `$1`-`$4`.
+const FILTER: &str = "($1::UUID IS NULL OR org_id = $1) AND ($2::UUID IS NULL OR api_key_id = $2) \
+                      AND ($3::TEXT IS NULL OR kind = $3) \
+                      AND (NOT $4::BOOLEAN OR acknowledged_at IS NULL)";
+
+pub struct PostgresUsageAnomalyRepo {
+    write_pool: PgPool,
+    read_pool: PgPool,
+}
+
+impl PostgresUsageAnomalyRepo {
+    pub fn new(write_pool: PgPool, read_pool: Option<PgPool>) -> Self {
+        let read_pool = read_pool.unwrap_or_else(|| write_pool.clone());
+        Self {
+            write_pool,
+            read_pool,
+        }
+    }
+
+    fn parse_anomaly(row: &PgRow) -> DbResult<UsageAnomaly> {
+        Ok(UsageAnomaly {
+            id: row.get("id"),
+            kind: row
+                .get::<String, _>("kind")
+                .parse()
+                .map_err(DbError::Internal)?,
+            org_id: row.get("org_id"),
+            api_key_id: row.get("api_key_id"),
+            detail: row.get("detail"),
+            summary: row.get("summary"),
+            observed: row.get("observed"),
+            expected: row.get("expected"),
+            score: row.get("score"),
+            window_start: row.get("window_start"),
+            window_end: row.get("window_end"),
+            rate_limited_until: row.get("rate_limited_until"),
+            acknowledged_at: row.get("acknowledged_at"),
+            acknowledged_by: row.get("acknowledged_by"),
+            created_at: row.get("created_at"),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl UsageAnomalyRepo for PostgresUsageAnomalyRepo {
+    async fn create(&self, input: CreateUsageAnomaly) -> DbResult<UsageAnomaly> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+
+        sqlx::query(
+            r#"
+            INSERT INTO usage_anomalies (
+                id, kind, org_id, api_key_id, detail, summary, observed, expected, score,
+                window_start, window_end, fingerprint, rate_limited_until, created_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+            "#,
+        )
+        .bind(id)
+        .bind(input.kind.as_str())
+        .bind(input.org_id)
+        .bind(input.api_key_id)
+        .bind(&input.detail)
+        .bind(&input.summary)
+        .bind(input.observed)
+        .bind(input.expected)
+        .bind(input.score)
+        .bind(input.window_start)
+        .bind(input.window_end)
+        .bind(&input.fingerprint)
+        .bind(input.rate_limited_until)
+        .bind(now)
+        .execute(&self.write_pool)
+        .await?;
+
+        Ok(UsageAnomaly {
+            id,
+            kind: input.kind,
+            org_id: input.org_id,
+            api_key_id: input.api_key_id,
+            detail: input.detail,
+            summary: input.summary,
+            observed: input.observed,
+            expected: input.expected,
+            score: input.score,
+            window_start: input.window_start,
+            window_end: input.window_end,
+            rate_limited_until: input.rate_limited_until,
+            acknowledged_at: None,
+            acknowledged_by: None,
+            created_at: now,
+        })
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<UsageAnomaly>> {
+        let sql = format!("SELECT {COLUMNS} FROM usage_anomalies WHERE id = $1");
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .fetch_optional(&self.read_pool)
+            .await?;
+
+        row.map(|r| Self::parse_anomaly(&r)).transpose()
+    }
+
+    async fn list(
+        &self,
+        filter: &UsageAnomalyFilter,
+        params: ListParams,
+    ) -> DbResult<ListResult<UsageAnomaly>> {
+        let limit = params.limit.unwrap_or(100);
+        let fetch_limit = limit + 1;
+
+        let (comparison, order, should_reverse) =
+            params.sort_order.cursor_query_params(params.direction);
+        let (cursor_clause, limit_param, order, should_reverse) = if params.cursor.is_some() {
+            (
+                format!("AND ROW(created_at, id) {} ROW($5, $6)", comparison),
+                "$7",
+                order,
+                should_reverse,
+            )
+        } else {
+            (String::new(), "$5", params.sort_order.as_sql(), false)
+        };
+
+        let sql = format!(
+            r#"
+            SELECT {COLUMNS}
+            FROM usage_anomalies
+            WHERE {FILTER} {cursor_clause}
+            ORDER BY created_at {order}, id {order}
+            LIMIT {limit_param}
+            "#
+        );
+
+        let mut q = sqlx::query(&sql)
+            .bind(filter.org_id)
+            .bind(filter.api_key_id)
+            .bind(filter.kind.map(|k| k.as_str()))
+            .bind(filter.unacknowledged);
+        if let Some(ref cursor) = params.cursor {
+            q = q.bind(cursor.created_at).bind(cursor.id);
+        }
+        let rows = q.bind(fetch_limit).fetch_all(&self.read_pool).await?;
+
+        let has_more = rows.len() as i64 > limit;
+        let mut items = rows
+            .iter()
+            .take(limit as usize)
+            .map(Self::parse_anomaly)
+            .collect::<DbResult<Vec<_>>>()?;
+
+        if should_reverse {
+            items.reverse();
+        }
+
+        let direction = if params.cursor.is_some() {
+            params.direction
+        } else {
+            CursorDirection::Forward
+        };
+        let cursors = PageCursors::from_items(
+            &items,
+            has_more,
+            direction,
+            params.cursor.as_ref(),
+            |anomaly| cursor_from_row(anomaly.created_at, anomaly.id),
+        );
+
+        Ok(ListResult::new(items, has_more, cursors))
+    }
+
+    async fn exists_since(&self, fingerprint: &str, since: DateTime<Utc>) -> DbResult<bool> {
+        let exists: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM usage_anomalies
+                WHERE fingerprint = $1 AND created_at >= $2
+            )
+            "#,
+        )
+        .bind(fingerprint)
+        .bind(since)
+        .fetch_one(&self.read_pool)
+        .await?;
+
+        Ok(exists)
+    }
+
+    async fn acknowledge(
+        &self,
+        id: Uuid,
+        acknowledged_by: Option<Uuid>,
+    ) -> DbResult<Option<UsageAnomaly>> {
+        let now = truncate_to_millis(Utc::now());
+        let sql = format!(
+            r#"
+            UPDATE usage_anomalies
+            SET acknowledged_at = COALESCE(acknowledged_at, $1),
+                acknowledged_by = CASE WHEN acknowledged_at IS NULL THEN $2
+                                       ELSE acknowledged_by END
+            WHERE id = $3
+            RETURNING {COLUMNS}
+            "#
+        );
+        let row = sqlx::query(&sql)
+            .bind(now)
+            .bind(acknowledged_by)
+            .bind(id)
+            .fetch_optional(&self.write_pool)
+            .await?;
+
+        row.map(|r| Self::parse_anomaly(&r)).transpose()
+    }
+}
@@ -566,6 +566,27 @@ impl UserRepo for PostgresUserRepo {
         Ok(row.get::<i64, _>("count"))
     }
 
+    async fn list_org_member_emails(
+        &self,
+        org_id: Uuid,
+        roles: &[String],
+    ) -> DbResult<Vec<String>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT DISTINCT u.email
+            FROM users u
+            INNER JOIN org_memberships om ON u.id = om.user_id
+            WHERE om.org_id = $1 AND om.role = ANY($2) AND u.email IS NOT NULL
+            ORDER BY u.email
+            "#,
+        )
+        .bind(org_id)
+        .bind(roles)
+        .fetch_all(&self.read_pool)
+        .await?;
+        Ok(rows.into_iter().map(|row| row.get("email")).collect())
+    }
+
     async fn count_total_org_memberships(&self) -> DbResult<i64> {
         let row = sqlx::query("SELECT COUNT(*) as count FROM org_memberships")
             .fetch_one(&self.read_pool)
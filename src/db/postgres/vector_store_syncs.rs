@@ -0,0 +1,306 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::{PgPool, Row, postgres::PgRow};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{
+            CursorDirection, ListParams, ListResult, PageCursors, VectorStoreSyncRepo,
+            cursor_from_row, truncate_to_millis,
+        },
+    },
+    models::{
+        CreateVectorStoreSync, VectorStoreSync, VectorStoreSyncCounts, VectorStoreSyncItem,
+        VectorStoreSyncStatus,
+    },
+};
+
+const COLUMNS: &str = "id, vector_store_id, source_name, connector, status::TEXT AS status, \
+                       files_added, files_updated, files_deleted, files_unchanged, files_failed, \
+                       error, started_at, finished_at";
+
+pub struct PostgresVectorStoreSyncRepo {
+    write_pool: PgPool,
+    read_pool: PgPool,
+}
+
+impl PostgresVectorStoreSyncRepo {
+    pub fn new(write_pool: PgPool, read_pool: Option<PgPool>) -> Self {
+        let read_pool = read_pool.unwrap_or_else(|| write_pool.clone());
+        Self {
+            write_pool,
+            read_pool,
+        }
+    }
+
+    fn parse_sync(row: &PgRow) -> DbResult<VectorStoreSync> {
+        Ok(VectorStoreSync {
+            id: row.get("id"),
+            vector_store_id: row.get("vector_store_id"),
+            source_name: row.get("source_name"),
+            connector: row.get("connector"),
+            status: row
+                .get::<String, _>("status")
+                .parse()
+                .map_err(DbError::Internal)?,
+            files_added: row.get("files_added"),
+            files_updated: row.get("files_updated"),
+            files_deleted: row.get("files_deleted"),
+            files_unchanged: row.get("files_unchanged"),
+            files_failed: row.get("files_failed"),
+            error: row.get("error"),
+            started_at: row.get("started_at"),
+            finished_at: row.get("finished_at"),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl VectorStoreSyncRepo for PostgresVectorStoreSyncRepo {
+    async fn create(&self, input: CreateVectorStoreSync) -> DbResult<VectorStoreSync> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+        let status = VectorStoreSyncStatus::Running;
+
+        sqlx::query(
+            r#"
+            INSERT INTO vector_store_syncs (id, vector_store_id, source_name, connector, status, started_at)
+            VALUES ($1, $2, $3, $4, $5::vector_store_sync_status, $6)
+            "#,
+        )
+        .bind(id)
+        .bind(input.vector_store_id)
+        .bind(&input.source_name)
+        .bind(&input.connector)
+        .bind(status.as_str())
+        .bind(now)
+        .execute(&self.write_pool)
+        .await?;
+
+        Ok(VectorStoreSync {
+            id,
+            vector_store_id: input.vector_store_id,
+            source_name: input.source_name,
+            connector: input.connector,
+            status,
+            files_added: 0,
+            files_updated: 0,
+            files_deleted: 0,
+            files_unchanged: 0,
+            files_failed: 0,
+            error: None,
+            started_at: now,
+            finished_at: None,
+        })
+    }
+
+    async fn finish(
+        &self,
+        id: Uuid,
+        status: VectorStoreSyncStatus,
+        counts: &VectorStoreSyncCounts,
+        error: Option<&str>,
+    ) -> DbResult<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE vector_store_syncs
+            SET status = $1::vector_store_sync_status, files_added = $2, files_updated = $3,
+                files_deleted = $4, files_unchanged = $5, files_failed = $6, error = $7,
+                finished_at = NOW()
+            WHERE id = $8
+            "#,
+        )
+        .bind(status.as_str())
+        .bind(counts.files_added)
+        .bind(counts.files_updated)
+        .bind(counts.files_deleted)
+        .bind(counts.files_unchanged)
+        .bind(counts.files_failed)
+        .bind(error)
+        .bind(id)
+        .execute(&self.write_pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+        Ok(())
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<VectorStoreSync>> {
+        let sql = format!("SELECT {COLUMNS} FROM vector_store_syncs WHERE id = $1");
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .fetch_optional(&self.read_pool)
+            .await?;
+
+        row.map(|r| Self::parse_sync(&r)).transpose()
+    }
+
+    async fn list(
+        &self,
+        vector_store_id: Uuid,
+        params: ListParams,
+    ) -> DbResult<ListResult<VectorStoreSync>> {
+        let limit = params.limit.unwrap_or(100);
+        let fetch_limit = limit + 1;
+
+        let (comparison, order, should_reverse) =
+            params.sort_order.cursor_query_params(params.direction);
+        let (cursor_clause, limit_idx, order, should_reverse) = if params.cursor.is_some() {
+            (
+                format!("AND ROW(started_at, id) {} ROW($2, $3)", comparison),
+                4,
+                order,
+                should_reverse,
+            )
+        } else {
+            (String::new(), 2, params.sort_order.as_sql(), false)
+        };
+
+        let sql = format!(
+            r#"
+            SELECT {COLUMNS}
+            FROM vector_store_syncs
+            WHERE vector_store_id = $1 {cursor_clause}
+            ORDER BY started_at {order}, id {order}
+            LIMIT ${limit_idx}
+            "#
+        );
+
+        let mut q = sqlx::query(&sql).bind(vector_store_id);
+        if let Some(ref cursor) = params.cursor {
+            q = q.bind(cursor.created_at).bind(cursor.id);
+        }
+        let rows = q.bind(fetch_limit).fetch_all(&self.read_pool).await?;
+
+        let has_more = rows.len() as i64 > limit;
+        let mut items = rows
+            .iter()
+            .take(limit as usize)
+            .map(Self::parse_sync)
+            .collect::<DbResult<Vec<_>>>()?;
+
+        if should_reverse {
+            items.reverse();
+        }
+
+        let direction = if params.cursor.is_some() {
+            params.direction
+        } else {
+            CursorDirection::Forward
+        };
+        let cursors = PageCursors::from_items(
+            &items,
+            has_more,
+            direction,
+            params.cursor.as_ref(),
+            |sync| cursor_from_row(sync.started_at, sync.id),
+        );
+
+        Ok(ListResult::new(items, has_more, cursors))
+    }
+
+    async fn latest_for_source(
+        &self,
+        vector_store_id: Uuid,
+        source_name: &str,
+    ) -> DbResult<Option<VectorStoreSync>> {
+        let sql = format!(
+            r#"
+            SELECT {COLUMNS}
+            FROM vector_store_syncs
+            WHERE vector_store_id = $1 AND source_name = $2
+            ORDER BY started_at DESC, id DESC
+            LIMIT 1
+            "#
+        );
+        // Read from the primary so a run started on the previous tick is
+        // never missed due to replica lag.
+        let row = sqlx::query(&sql)
+            .bind(vector_store_id)
+            .bind(source_name)
+            .fetch_optional(&self.write_pool)
+            .await?;
+
+        row.map(|r| Self::parse_sync(&r)).transpose()
+    }
+
+    async fn list_items(
+        &self,
+        vector_store_id: Uuid,
+        source_name: &str,
+    ) -> DbResult<Vec<VectorStoreSyncItem>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT source_key, version, file_id
+            FROM vector_store_sync_items
+            WHERE vector_store_id = $1 AND source_name = $2
+            "#,
+        )
+        .bind(vector_store_id)
+        .bind(source_name)
+        .fetch_all(&self.write_pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| VectorStoreSyncItem {
+                source_key: row.get("source_key"),
+                version: row.get("version"),
+                file_id: row.get("file_id"),
+            })
+            .collect())
+    }
+
+    async fn upsert_item(
+        &self,
+        vector_store_id: Uuid,
+        source_name: &str,
+        item: &VectorStoreSyncItem,
+    ) -> DbResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO vector_store_sync_items (vector_store_id, source_name, source_key, version, file_id, synced_at)
+            VALUES ($1, $2, $3, $4, $5, NOW())
+            ON CONFLICT (vector_store_id, source_name, source_key) DO UPDATE SET
+                version = EXCLUDED.version,
+                file_id = EXCLUDED.file_id,
+                synced_at = EXCLUDED.synced_at
+            "#,
+        )
+        .bind(vector_store_id)
+        .bind(source_name)
+        .bind(&item.source_key)
+        .bind(&item.version)
+        .bind(item.file_id)
+        .execute(&self.write_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete_item(
+        &self,
+        vector_store_id: Uuid,
+        source_name: &str,
+        source_key: &str,
+    ) -> DbResult<()> {
+        sqlx::query(
+            r#"
+            DELETE FROM vector_store_sync_items
+            WHERE vector_store_id = $1 AND source_name = $2 AND source_key = $3
+            "#,
+        )
+        .bind(vector_store_id)
+        .bind(source_name)
+        .bind(source_key)
+        .execute(&self.write_pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -12,8 +12,9 @@ use crate::{
     },
     models::{
         AddFileToVectorStore, ChunkingStrategy, CreateVectorStore, ExpiresAfter, FileCounts,
-        FileError, OBJECT_TYPE_VECTOR_STORE, OBJECT_TYPE_VECTOR_STORE_FILE, UpdateVectorStore,
-        VectorStore, VectorStoreFile, VectorStoreFileStatus, VectorStoreOwnerType,
+        FileError, HybridSearchOptions, OBJECT_TYPE_VECTOR_STORE, OBJECT_TYPE_VECTOR_STORE_FILE,
+        UpdateVectorStore, VectorStore, VectorStoreFile, VectorStoreFileStatus,
+        VectorStoreOwnerType,
     },
 };
 
@@ -53,6 +54,15 @@ impl PostgresVectorStoresRepo {
         }
     }
 
+    fn parse_hybrid_search(
+        json_value: Option<serde_json::Value>,
+    ) -> DbResult<Option<HybridSearchOptions>> {
+        match json_value {
+            Some(v) => serde_json::from_value(v).map_err(|e| DbError::Internal(e.to_string())),
+            None => Ok(None),
+        }
+    }
+
     fn parse_chunking_strategy(
         json_value: Option<serde_json::Value>,
     ) -> DbResult<Option<ChunkingStrategy>> {
@@ -101,6 +111,7 @@ impl PostgresVectorStoresRepo {
             file_counts: Self::parse_file_counts(row.get("file_counts"))?,
             metadata: Self::parse_metadata(row.get("metadata"))?,
             expires_after: Self::parse_expires_after(row.get("expires_after"))?,
+            hybrid_search: Self::parse_hybrid_search(row.get("hybrid_search"))?,
             expires_at: row.get("expires_at"),
             last_active_at: row.get("last_active_at"),
             created_at: row.get("created_at"),
@@ -158,18 +169,25 @@ impl VectorStoresRepo for PostgresVectorStoresRepo {
             .map(|m| serde_json::to_value(&m))
             .transpose()
             .map_err(|e| DbError::Internal(e.to_string()))?;
+        let now = Utc::now();
+        let expires_at = input.expires_after.as_ref().map(|e| e.expires_at(now));
         let expires_after_json = input
             .expires_after
             .map(|e| serde_json::to_value(&e))
             .transpose()
             .map_err(|e| DbError::Internal(e.to_string()))?;
+        let hybrid_search_json = input
+            .hybrid_search
+            .map(|h| serde_json::to_value(&h))
+            .transpose()
+            .map_err(|e| DbError::Internal(e.to_string()))?;
 
         let row = sqlx::query(
             r#"
-            INSERT INTO vector_stores (id, owner_type, owner_id, name, description, embedding_model, embedding_dimensions, metadata, expires_after)
-            VALUES ($1, $2::vector_store_owner_type, $3, $4, $5, $6, $7, $8, $9)
+            INSERT INTO vector_stores (id, owner_type, owner_id, name, description, embedding_model, embedding_dimensions, metadata, expires_after, expires_at, last_active_at, hybrid_search)
+            VALUES ($1, $2::vector_store_owner_type, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
             RETURNING id, owner_type::TEXT, owner_id, name, description, status::TEXT, embedding_model, embedding_dimensions,
-                      usage_bytes, file_counts, metadata, expires_after, expires_at, last_active_at, created_at, updated_at
+                      usage_bytes, file_counts, metadata, expires_after, hybrid_search, expires_at, last_active_at, created_at, updated_at
             "#,
         )
         .bind(id)
@@ -181,6 +199,9 @@ impl VectorStoresRepo for PostgresVectorStoresRepo {
         .bind(input.embedding_dimensions)
         .bind(&metadata_json)
         .bind(&expires_after_json)
+        .bind(expires_at)
+        .bind(now)
+        .bind(&hybrid_search_json)
         .fetch_one(&self.write_pool)
         .await?;
 
@@ -191,7 +212,7 @@ impl VectorStoresRepo for PostgresVectorStoresRepo {
         let result = sqlx::query(
             r#"
             SELECT id, owner_type::TEXT, owner_id, name, description, status::TEXT, embedding_model, embedding_dimensions,
-                   usage_bytes, file_counts, metadata, expires_after, expires_at, last_active_at, created_at, updated_at
+                   usage_bytes, file_counts, metadata, expires_after, hybrid_search, expires_at, last_active_at, created_at, updated_at
             FROM vector_stores
             WHERE id = $1 AND deleted_at IS NULL
             "#,
@@ -210,7 +231,7 @@ impl VectorStoresRepo for PostgresVectorStoresRepo {
         let result = sqlx::query(
             r#"
             SELECT vs.id, vs.owner_type::TEXT, vs.owner_id, vs.name, vs.description, vs.status::TEXT, vs.embedding_model, vs.embedding_dimensions,
-                   vs.usage_bytes, vs.file_counts, vs.metadata, vs.expires_after, vs.expires_at, vs.last_active_at, vs.created_at, vs.updated_at
+                   vs.usage_bytes, vs.file_counts, vs.metadata, vs.expires_after, vs.hybrid_search, vs.expires_at, vs.last_active_at, vs.created_at, vs.updated_at
             FROM vector_stores vs
             WHERE vs.id = $1 AND vs.deleted_at IS NULL
             AND (
@@ -253,7 +274,7 @@ impl VectorStoresRepo for PostgresVectorStoresRepo {
         let result = sqlx::query(
             r#"
             SELECT id, owner_type::TEXT, owner_id, name, description, status::TEXT, embedding_model, embedding_dimensions,
-                   usage_bytes, file_counts, metadata, expires_after, expires_at, last_active_at, created_at, updated_at
+                   usage_bytes, file_counts, metadata, expires_after, hybrid_search, expires_at, last_active_at, created_at, updated_at
             FROM vector_stores
             WHERE owner_type = $1::vector_store_owner_type AND owner_id = $2 AND name = $3 AND deleted_at IS NULL
             "#,
@@ -293,7 +314,7 @@ impl VectorStoresRepo for PostgresVectorStoresRepo {
             let query = format!(
                 r#"
                 SELECT id, owner_type::TEXT, owner_id, name, description, status::TEXT, embedding_model, embedding_dimensions,
-                       usage_bytes, file_counts, metadata, expires_after, expires_at, last_active_at, created_at, updated_at
+                       usage_bytes, file_counts, metadata, expires_after, hybrid_search, expires_at, last_active_at, created_at, updated_at
                 FROM vector_stores
                 WHERE owner_type = $1::vector_store_owner_type AND owner_id = $2
                 AND ROW(updated_at, id) {} ROW($3, $4)
@@ -341,7 +362,7 @@ impl VectorStoresRepo for PostgresVectorStoresRepo {
             format!(
                 r#"
                 SELECT id, owner_type::TEXT, owner_id, name, description, status::TEXT, embedding_model, embedding_dimensions,
-                       usage_bytes, file_counts, metadata, expires_after, expires_at, last_active_at, created_at, updated_at
+                       usage_bytes, file_counts, metadata, expires_after, hybrid_search, expires_at, last_active_at, created_at, updated_at
                 FROM vector_stores
                 WHERE owner_type = $1::vector_store_owner_type AND owner_id = $2
                 ORDER BY updated_at {}, id {}
@@ -353,7 +374,7 @@ impl VectorStoresRepo for PostgresVectorStoresRepo {
             format!(
                 r#"
                 SELECT id, owner_type::TEXT, owner_id, name, description, status::TEXT, embedding_model, embedding_dimensions,
-                       usage_bytes, file_counts, metadata, expires_after, expires_at, last_active_at, created_at, updated_at
+                       usage_bytes, file_counts, metadata, expires_after, hybrid_search, expires_at, last_active_at, created_at, updated_at
                 FROM vector_stores
                 WHERE owner_type = $1::vector_store_owner_type AND owner_id = $2 AND deleted_at IS NULL
                 ORDER BY updated_at {}, id {}
@@ -471,7 +492,7 @@ impl VectorStoresRepo for PostgresVectorStoresRepo {
             let query = format!(
                 r#"
                 SELECT id, owner_type::TEXT, owner_id, name, description, status::TEXT, embedding_model, embedding_dimensions,
-                       usage_bytes, file_counts, metadata, expires_after, expires_at, last_active_at, created_at, updated_at
+                       usage_bytes, file_counts, metadata, expires_after, hybrid_search, expires_at, last_active_at, created_at, updated_at
                 FROM vector_stores
                 WHERE ({})
                 AND ROW(updated_at, id) {} ROW(${}, ${})
@@ -529,7 +550,7 @@ impl VectorStoresRepo for PostgresVectorStoresRepo {
             format!(
                 r#"
                 SELECT id, owner_type::TEXT, owner_id, name, description, status::TEXT, embedding_model, embedding_dimensions,
-                       usage_bytes, file_counts, metadata, expires_after, expires_at, last_active_at, created_at, updated_at
+                       usage_bytes, file_counts, metadata, expires_after, hybrid_search, expires_at, last_active_at, created_at, updated_at
                 FROM vector_stores
                 WHERE ({})
                 ORDER BY updated_at {}, id {}
@@ -541,7 +562,7 @@ impl VectorStoresRepo for PostgresVectorStoresRepo {
             format!(
                 r#"
                 SELECT id, owner_type::TEXT, owner_id, name, description, status::TEXT, embedding_model, embedding_dimensions,
-                       usage_bytes, file_counts, metadata, expires_after, expires_at, last_active_at, created_at, updated_at
+                       usage_bytes, file_counts, metadata, expires_after, hybrid_search, expires_at, last_active_at, created_at, updated_at
                 FROM vector_stores
                 WHERE ({}) AND deleted_at IS NULL
                 ORDER BY updated_at {}, id {}
@@ -600,7 +621,7 @@ impl VectorStoresRepo for PostgresVectorStoresRepo {
             let query = format!(
                 r#"
                 SELECT id, owner_type::TEXT, owner_id, name, description, status::TEXT, embedding_model, embedding_dimensions,
-                       usage_bytes, file_counts, metadata, expires_after, expires_at, last_active_at, created_at, updated_at
+                       usage_bytes, file_counts, metadata, expires_after, hybrid_search, expires_at, last_active_at, created_at, updated_at
                 FROM vector_stores
                 WHERE ROW(updated_at, id) {} ROW($1, $2)
                 {}
@@ -644,7 +665,7 @@ impl VectorStoresRepo for PostgresVectorStoresRepo {
             format!(
                 r#"
                 SELECT id, owner_type::TEXT, owner_id, name, description, status::TEXT, embedding_model, embedding_dimensions,
-                       usage_bytes, file_counts, metadata, expires_after, expires_at, last_active_at, created_at, updated_at
+                       usage_bytes, file_counts, metadata, expires_after, hybrid_search, expires_at, last_active_at, created_at, updated_at
                 FROM vector_stores
                 ORDER BY updated_at {}, id {}
                 LIMIT $1
@@ -655,7 +676,7 @@ impl VectorStoresRepo for PostgresVectorStoresRepo {
             format!(
                 r#"
                 SELECT id, owner_type::TEXT, owner_id, name, description, status::TEXT, embedding_model, embedding_dimensions,
-                       usage_bytes, file_counts, metadata, expires_after, expires_at, last_active_at, created_at, updated_at
+                       usage_bytes, file_counts, metadata, expires_after, hybrid_search, expires_at, last_active_at, created_at, updated_at
                 FROM vector_stores
                 WHERE deleted_at IS NULL
                 ORDER BY updated_at {}, id {}
@@ -699,7 +720,7 @@ impl VectorStoresRepo for PostgresVectorStoresRepo {
         let current = sqlx::query(
             r#"
             SELECT id, owner_type::TEXT, owner_id, name, description, status::TEXT, embedding_model, embedding_dimensions,
-                   usage_bytes, file_counts, metadata, expires_after, expires_at, last_active_at, created_at, updated_at
+                   usage_bytes, file_counts, metadata, expires_after, hybrid_search, expires_at, last_active_at, created_at, updated_at
             FROM vector_stores
             WHERE id = $1 AND deleted_at IS NULL
             FOR UPDATE
@@ -714,6 +735,7 @@ impl VectorStoresRepo for PostgresVectorStoresRepo {
         let current_description: Option<String> = current.get("description");
         let current_metadata: Option<serde_json::Value> = current.get("metadata");
         let current_expires_after: Option<serde_json::Value> = current.get("expires_after");
+        let current_hybrid_search: Option<serde_json::Value> = current.get("hybrid_search");
 
         let new_name = input.name.unwrap_or(current_name);
         let new_description = input.description.or(current_description);
@@ -723,20 +745,32 @@ impl VectorStoresRepo for PostgresVectorStoresRepo {
             .transpose()
             .map_err(|e| DbError::Internal(e.to_string()))?
             .or(current_metadata);
+        let last_active_at: Option<DateTime<Utc>> = current.get("last_active_at");
+        // A new policy is measured from the store's current anchor
+        let new_expires_at: Option<DateTime<Utc>> = match input.expires_after {
+            Some(ref policy) => Some(policy.expires_at(last_active_at.unwrap_or_else(Utc::now))),
+            None => current.get("expires_at"),
+        };
         let new_expires_after = input
             .expires_after
             .map(|e| serde_json::to_value(&e))
             .transpose()
             .map_err(|e| DbError::Internal(e.to_string()))?
             .or(current_expires_after);
+        let new_hybrid_search = input
+            .hybrid_search
+            .map(|h| serde_json::to_value(&h))
+            .transpose()
+            .map_err(|e| DbError::Internal(e.to_string()))?
+            .or(current_hybrid_search);
 
         let row = sqlx::query(
             r#"
             UPDATE vector_stores
-            SET name = $1, description = $2, metadata = $3, expires_after = $4, updated_at = NOW()
+            SET name = $1, description = $2, metadata = $3, expires_after = $4, expires_at = $6, hybrid_search = $7, updated_at = NOW()
             WHERE id = $5 AND deleted_at IS NULL
             RETURNING id, owner_type::TEXT, owner_id, name, description, status::TEXT, embedding_model, embedding_dimensions,
-                      usage_bytes, file_counts, metadata, expires_after, expires_at, last_active_at, created_at, updated_at
+                      usage_bytes, file_counts, metadata, expires_after, hybrid_search, expires_at, last_active_at, created_at, updated_at
             "#,
         )
         .bind(&new_name)
@@ -744,6 +778,8 @@ impl VectorStoresRepo for PostgresVectorStoresRepo {
         .bind(&new_metadata)
         .bind(&new_expires_after)
         .bind(id)
+        .bind(new_expires_at)
+        .bind(&new_hybrid_search)
         .fetch_optional(&mut *tx)
         .await?
         .ok_or(DbError::NotFound)?;
@@ -771,6 +807,7 @@ impl VectorStoresRepo for PostgresVectorStoresRepo {
             file_counts: Self::parse_file_counts(row.get("file_counts"))?,
             metadata: Self::parse_metadata(row.get("metadata"))?,
             expires_after: Self::parse_expires_after(row.get("expires_after"))?,
+            hybrid_search: Self::parse_hybrid_search(row.get("hybrid_search"))?,
             expires_at: row.get("expires_at"),
             last_active_at: row.get("last_active_at"),
             created_at: row.get("created_at"),
@@ -798,24 +835,50 @@ impl VectorStoresRepo for PostgresVectorStoresRepo {
     }
 
     async fn touch_vector_store(&self, id: Uuid) -> DbResult<()> {
-        let result = sqlx::query(
+        // Expired stores stay expired; activity doesn't revive them
+        let row = sqlx::query(
             r#"
             UPDATE vector_stores
-            SET last_active_at = NOW(), updated_at = NOW()
+            SET last_active_at = CASE WHEN status = 'expired' THEN last_active_at ELSE NOW() END,
+                expires_at = CASE
+                    WHEN status = 'expired' THEN expires_at
+                    WHEN expires_after IS NULL THEN NULL
+                    ELSE NOW() + make_interval(days => (expires_after->>'days')::INT)
+                END,
+                updated_at = NOW()
             WHERE id = $1 AND deleted_at IS NULL
+            RETURNING id
             "#,
         )
         .bind(id)
-        .execute(&self.write_pool)
+        .fetch_optional(&self.write_pool)
         .await?;
 
-        if result.rows_affected() == 0 {
+        if row.is_none() {
             return Err(DbError::NotFound);
         }
 
         Ok(())
     }
 
+    async fn expire_vector_stores(&self, now: DateTime<Utc>) -> DbResult<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE vector_stores
+            SET status = 'expired', updated_at = NOW()
+            WHERE deleted_at IS NULL
+              AND status != 'expired'
+              AND expires_at IS NOT NULL
+              AND expires_at <= $1
+            "#,
+        )
+        .bind(now)
+        .execute(&self.write_pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
     async fn hard_delete_vector_store(&self, id: Uuid) -> DbResult<()> {
         // First delete all vector_store_files links
         sqlx::query(
@@ -853,7 +916,7 @@ impl VectorStoresRepo for PostgresVectorStoresRepo {
         let rows = sqlx::query(
             r#"
             SELECT id, owner_type::TEXT, owner_id, name, description, status::TEXT, embedding_model, embedding_dimensions,
-                   usage_bytes, file_counts, metadata, expires_after, expires_at, last_active_at, created_at, updated_at
+                   usage_bytes, file_counts, metadata, expires_after, hybrid_search, expires_at, last_active_at, created_at, updated_at
             FROM vector_stores
             WHERE deleted_at IS NOT NULL AND deleted_at < $1
             "#,
@@ -0,0 +1,31 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    db::error::DbResult,
+    models::{AdminPasskey, CreateAdminPasskey},
+};
+
+/// Storage for admin WebAuthn credentials (any number per user).
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait AdminPasskeyRepo: Send + Sync {
+    /// Store a newly registered passkey.
+    ///
+    /// Returns `DbError::Conflict` if the credential ID is already registered.
+    async fn create(&self, input: CreateAdminPasskey) -> DbResult<AdminPasskey>;
+
+    /// List a user's passkeys, oldest first.
+    async fn list_by_user(&self, user_id: Uuid) -> DbResult<Vec<AdminPasskey>>;
+
+    /// Look up a passkey by the credential ID the authenticator presented.
+    async fn get_by_credential_id(&self, credential_id: &str) -> DbResult<Option<AdminPasskey>>;
+
+    /// Record a successful authentication, replacing the stored credential
+    /// (its signature counter may have advanced).
+    async fn record_use(&self, id: Uuid, credential: &str) -> DbResult<()>;
+
+    /// Remove one of a user's passkeys. Returns `false` if it doesn't exist
+    /// or belongs to another user.
+    async fn delete(&self, user_id: Uuid, id: Uuid) -> DbResult<bool>;
+}
@@ -0,0 +1,33 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{db::error::DbResult, models::AdminTotpEnrollment};
+
+/// Storage for admin TOTP authenticators (one per user).
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait AdminTotpRepo: Send + Sync {
+    /// Get a user's enrollment, pending or verified.
+    async fn get(&self, user_id: Uuid) -> DbResult<Option<AdminTotpEnrollment>>;
+
+    /// Start (or restart) enrollment with a new secret.
+    ///
+    /// Replaces any existing row and clears its verification state, so callers
+    /// must refuse to re-enroll over a verified authenticator.
+    async fn upsert_pending(&self, user_id: Uuid, secret: &str) -> DbResult<AdminTotpEnrollment>;
+
+    /// Confirm a pending enrollment, recording `step` as the first used code.
+    ///
+    /// Returns `false` if there is no pending enrollment.
+    async fn mark_verified(&self, user_id: Uuid, step: i64) -> DbResult<bool>;
+
+    /// Record a successful code on a verified enrollment.
+    ///
+    /// Atomically advances `last_used_step`; returns `false` if `step` is not
+    /// newer than the last accepted one (a replay, or a concurrent request
+    /// that used the same code).
+    async fn record_use(&self, user_id: Uuid, step: i64) -> DbResult<bool>;
+
+    /// Remove a user's enrollment. Returns `false` if none existed.
+    async fn delete(&self, user_id: Uuid) -> DbResult<bool>;
+}
@@ -0,0 +1,61 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    db::error::DbResult,
+    models::{
+        Agent, AgentRun, AgentRunOutcome, AgentRunStep, CreateAgent, CreateAgentRun, UpdateAgent,
+    },
+};
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait AgentRepo: Send + Sync {
+    // ==================== Agents ====================
+
+    /// Create an agent. Fails with `Conflict` if the organization already
+    /// has an agent with the same name.
+    async fn create_agent(&self, org_id: Uuid, input: CreateAgent) -> DbResult<Agent>;
+
+    async fn get_agent(&self, id: Uuid) -> DbResult<Option<Agent>>;
+
+    /// An organization's agents, ordered by name.
+    async fn list_agents(&self, org_id: Uuid) -> DbResult<Vec<Agent>>;
+
+    /// Update an agent. Fails with `NotFound` if it doesn't exist.
+    async fn update_agent(&self, id: Uuid, input: UpdateAgent) -> DbResult<Agent>;
+
+    /// Delete an agent with its runs. Fails with `NotFound` if it doesn't
+    /// exist.
+    async fn delete_agent(&self, id: Uuid) -> DbResult<()>;
+
+    // ==================== Runs ====================
+
+    /// Record a run that is starting.
+    async fn create_run(&self, input: CreateAgentRun) -> DbResult<AgentRun>;
+
+    async fn get_run(&self, id: Uuid) -> DbResult<Option<AgentRun>>;
+
+    /// An agent's runs, newest first, limited to one project when given.
+    async fn list_runs(
+        &self,
+        agent_id: Uuid,
+        project_id: Option<Uuid>,
+        limit: i64,
+    ) -> DbResult<Vec<AgentRun>>;
+
+    /// Record a step of a run. Steps are numbered by the caller.
+    async fn add_step(
+        &self,
+        run_id: Uuid,
+        sequence: i32,
+        step_type: &str,
+        content: &serde_json::Value,
+    ) -> DbResult<AgentRunStep>;
+
+    /// A run's steps in order.
+    async fn list_steps(&self, run_id: Uuid) -> DbResult<Vec<AgentRunStep>>;
+
+    /// Store how a run finished. Fails with `NotFound` if it doesn't exist.
+    async fn finish_run(&self, id: Uuid, outcome: &AgentRunOutcome) -> DbResult<AgentRun>;
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
@@ -5,13 +7,18 @@ use uuid::Uuid;
 use super::{ListParams, ListResult};
 use crate::{
     db::error::DbResult,
-    models::{ApiKey, ApiKeyWithOwner, CachedApiKey, CreateApiKey},
+    models::{ApiKey, ApiKeyHash, ApiKeyWithOwner, CachedApiKey, CreateApiKey},
 };
 
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 pub trait ApiKeyRepo: Send + Sync {
-    async fn create(&self, input: CreateApiKey, key_hash: &str) -> DbResult<ApiKey>;
+    /// Create a key stored under the legacy SHA-256 scheme.
+    async fn create(&self, input: CreateApiKey, key_hash: &str) -> DbResult<ApiKey> {
+        self.create_with_hash(input, &ApiKeyHash::sha256(key_hash))
+            .await
+    }
+    async fn create_with_hash(&self, input: CreateApiKey, hash: &ApiKeyHash) -> DbResult<ApiKey>;
     async fn get_by_id(&self, id: Uuid) -> DbResult<Option<ApiKey>>;
     async fn get_by_hash(&self, key_hash: &str) -> DbResult<Option<ApiKeyWithOwner>>;
     async fn list_by_org(&self, org_id: Uuid, params: ListParams) -> DbResult<ListResult<ApiKey>>;
@@ -31,6 +38,10 @@ pub trait ApiKeyRepo: Send + Sync {
     /// Count every active API key in the system (not revoked, not expired). Used by
     /// access-review summaries to avoid iterating users.
     async fn count_total_active(&self) -> DbResult<i64>;
+    /// Count the active API keys (not revoked, not expired) attributed to an
+    /// organization: its own keys and those of its teams, projects and
+    /// service accounts. User-owned keys aren't counted.
+    async fn count_active_by_org(&self, org_id: Uuid) -> DbResult<i64>;
     async fn revoke(&self, id: Uuid) -> DbResult<()>;
     async fn update_last_used(&self, id: Uuid) -> DbResult<()>;
 
@@ -75,8 +86,34 @@ pub trait ApiKeyRepo: Send + Sync {
         new_key_input: CreateApiKey,
         new_key_hash: &str,
         grace_until: DateTime<Utc>,
+    ) -> DbResult<ApiKey> {
+        self.rotate_with_hash(
+            old_key_id,
+            new_key_input,
+            &ApiKeyHash::sha256(new_key_hash),
+            grace_until,
+        )
+        .await
+    }
+
+    /// [`rotate`](Self::rotate), storing the new key under any hash scheme.
+    async fn rotate_with_hash(
+        &self,
+        old_key_id: Uuid,
+        new_key_input: CreateApiKey,
+        new_key_hash: &ApiKeyHash,
+        grace_until: DateTime<Utc>,
     ) -> DbResult<ApiKey>;
 
+    /// Re-hash a key under a new scheme, if it is still stored under
+    /// `current_hash`. Returns false if the key was already re-hashed.
+    ///
+    /// `key_prefix` is left unchanged so the key stays recognizable.
+    async fn update_hash(&self, id: Uuid, current_hash: &str, hash: &ApiKeyHash) -> DbResult<bool>;
+
+    /// Replace the key's labels (an empty map removes them).
+    async fn set_labels(&self, id: Uuid, labels: &BTreeMap<String, String>) -> DbResult<ApiKey>;
+
     /// Get the key hashes for all active API keys owned by a service account.
     ///
     /// Used for cache invalidation when service account roles are updated.
@@ -0,0 +1,46 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use super::{ListParams, ListResult};
+use crate::{
+    db::error::DbResult,
+    models::{CertMatchType, ClientCertMapping, CreateClientCertMapping},
+};
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait ClientCertMappingRepo: Send + Sync {
+    /// Map a certificate identifier to a service account.
+    ///
+    /// `input.match_value` must already be normalized. Returns
+    /// `DbError::Conflict` if the identifier is already mapped, since a
+    /// certificate must resolve to exactly one service account.
+    async fn create(
+        &self,
+        org_id: Uuid,
+        service_account_id: Uuid,
+        input: CreateClientCertMapping,
+    ) -> DbResult<ClientCertMapping>;
+
+    /// Get a mapping by ID.
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<ClientCertMapping>>;
+
+    /// List mappings for a service account.
+    async fn list_by_service_account(
+        &self,
+        service_account_id: Uuid,
+        params: ListParams,
+    ) -> DbResult<ListResult<ClientCertMapping>>;
+
+    /// Find mappings matching any of the given `(match_type, normalized value)`
+    /// identifiers extracted from a presented certificate.
+    ///
+    /// Mappings whose service account has been deleted are excluded.
+    async fn find_by_identifiers(
+        &self,
+        identifiers: &[(CertMatchType, String)],
+    ) -> DbResult<Vec<ClientCertMapping>>;
+
+    /// Delete a mapping.
+    async fn delete(&self, id: Uuid) -> DbResult<()>;
+}
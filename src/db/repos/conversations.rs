@@ -6,8 +6,9 @@ use super::{ListParams, ListResult};
 use crate::{
     db::error::DbResult,
     models::{
-        AppendMessages, Conversation, ConversationOwnerType, ConversationWithProject,
-        CreateConversation, Message, UpdateConversation,
+        AppendMessages, Conversation, ConversationBranch, ConversationOwnerType,
+        ConversationWithProject, CreateConversation, ForkConversation, Message,
+        SetConversationSummary, UpdateConversation,
     },
 };
 
@@ -62,6 +63,23 @@ pub trait ConversationRepo: Send + Sync {
     /// - `pin_order = None`: Unpin the conversation
     async fn set_pin_order(&self, id: Uuid, pin_order: Option<i32>) -> DbResult<Conversation>;
 
+    // ==================== Branching ====================
+
+    /// List a conversation's branches, oldest first.
+    ///
+    /// Returns an empty list for conversations that have never been forked.
+    async fn list_branches(&self, id: Uuid) -> DbResult<Vec<ConversationBranch>>;
+
+    /// Fork the conversation at `input.message_index` and make the new branch active.
+    ///
+    /// The first fork also records the current history as the root branch so
+    /// it can be switched back to. Returns `DbError::Validation` when the
+    /// index is out of range.
+    async fn fork(&self, id: Uuid, input: ForkConversation) -> DbResult<ConversationBranch>;
+
+    /// Make `branch_id` the active branch, copying its messages onto the conversation.
+    async fn switch_branch(&self, id: Uuid, branch_id: Uuid) -> DbResult<Conversation>;
+
     /// List all conversations accessible to a user
     ///
     /// Returns both:
@@ -77,6 +95,28 @@ pub trait ConversationRepo: Send + Sync {
         include_deleted: bool,
     ) -> DbResult<Vec<ConversationWithProject>>;
 
+    // ==================== Summary Operations ====================
+
+    /// List conversations whose background summary is missing or stale.
+    ///
+    /// `org_id = Some(..)` selects project conversations in that organization;
+    /// `None` selects personal (user-owned) conversations. A conversation is
+    /// stale once it has gained `resummarize_after` messages since its last
+    /// summary, or has fewer messages than the summary covers (e.g. after a
+    /// branch switch). Most recently updated conversations come first.
+    async fn list_needing_summary(
+        &self,
+        org_id: Option<Uuid>,
+        min_messages: u32,
+        resummarize_after: u32,
+        limit: i64,
+    ) -> DbResult<Vec<Conversation>>;
+
+    /// Store a generated summary (and optionally a title) on a conversation.
+    ///
+    /// Does not count as user activity, so `updated_at` is left unchanged.
+    async fn set_summary(&self, id: Uuid, input: SetConversationSummary) -> DbResult<()>;
+
     // ==================== Retention Operations ====================
 
     /// Hard-delete conversations that were soft-deleted before the given cutoff date.
@@ -0,0 +1,33 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use super::{ListParams, ListResult};
+use crate::{
+    db::error::DbResult,
+    models::{EmailLogEntry, EmailLogFilter, NewEmailLogEntry},
+};
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait EmailLogRepo: Send + Sync {
+    /// Record a send attempt.
+    async fn create(&self, input: NewEmailLogEntry) -> DbResult<EmailLogEntry>;
+
+    /// List send attempts matching `filter`, newest first by default.
+    async fn list(
+        &self,
+        filter: EmailLogFilter,
+        params: ListParams,
+    ) -> DbResult<ListResult<EmailLogEntry>>;
+
+    /// Delete send attempts older than the given cutoff date.
+    ///
+    /// Deletes in batches to avoid locking the database.
+    /// Returns the total number of records deleted.
+    async fn delete_before(
+        &self,
+        cutoff: DateTime<Utc>,
+        batch_size: u32,
+        max_deletes: u64,
+    ) -> DbResult<u64>;
+}
@@ -0,0 +1,42 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use super::DateRange;
+use crate::{
+    db::error::DbResult,
+    models::{FederatedGateway, FederatedUsageRow, FederationReport},
+};
+
+/// Storage for satellite reports on a federation hub.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait FederationRepo: Send + Sync {
+    /// Store a satellite report.
+    ///
+    /// Upserts the gateway's metadata and health snapshot, and replaces all
+    /// stored usage for each date present in the report.
+    async fn record_report(
+        &self,
+        report: &FederationReport,
+        received_at: DateTime<Utc>,
+    ) -> DbResult<()>;
+
+    /// All gateways that have reported, ordered by ID.
+    async fn list_gateways(&self) -> DbResult<Vec<FederatedGateway>>;
+
+    /// Get a single gateway by ID.
+    async fn get_gateway(&self, id: &str) -> DbResult<Option<FederatedGateway>>;
+
+    /// Forget a gateway and all of its reported usage.
+    ///
+    /// Returns `false` if the gateway had never reported.
+    async fn delete_gateway(&self, id: &str) -> DbResult<bool>;
+
+    /// Reported usage rows within an inclusive date range, optionally for a
+    /// single gateway. Ordered by date, then gateway, then model.
+    async fn list_usage(
+        &self,
+        range: DateRange,
+        gateway_id: Option<&str>,
+    ) -> DbResult<Vec<FederatedUsageRow>>;
+}
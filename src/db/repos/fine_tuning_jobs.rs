@@ -0,0 +1,40 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    db::error::DbResult,
+    models::{CreateFineTuningJobRecord, FineTuningJobRecord, FineTuningJobStatus},
+};
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait FineTuningJobRepo: Send + Sync {
+    async fn create(&self, input: CreateFineTuningJobRecord) -> DbResult<FineTuningJobRecord>;
+
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<FineTuningJobRecord>>;
+
+    /// Jobs of an organization, newest first. With `project_id`, only that
+    /// project's jobs.
+    async fn list(
+        &self,
+        org_id: Uuid,
+        project_id: Option<Uuid>,
+        limit: i64,
+    ) -> DbResult<Vec<FineTuningJobRecord>>;
+
+    /// Jobs not yet completed: still running, or finished without their
+    /// usage recorded.
+    async fn list_incomplete(&self, limit: i64) -> DbResult<Vec<FineTuningJobRecord>>;
+
+    /// Store the provider's latest status. Fails with `NotFound` if the job
+    /// doesn't exist.
+    async fn update_status(
+        &self,
+        id: Uuid,
+        status: &FineTuningJobStatus,
+    ) -> DbResult<FineTuningJobRecord>;
+
+    /// Mark a finished job as completed. Returns false if it already was,
+    /// so only one caller records its usage.
+    async fn mark_completed(&self, id: Uuid) -> DbResult<bool>;
+}
@@ -0,0 +1,26 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    db::error::DbResult,
+    models::{CreateHttpTool, HttpTool, UpdateHttpTool},
+};
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait HttpToolRepo: Send + Sync {
+    /// Register an HTTP tool. Fails with `Conflict` if the organization
+    /// already has a tool with the same name.
+    async fn create(&self, org_id: Uuid, input: CreateHttpTool) -> DbResult<HttpTool>;
+
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<HttpTool>>;
+
+    /// An organization's HTTP tools, ordered by name.
+    async fn list_by_org(&self, org_id: Uuid) -> DbResult<Vec<HttpTool>>;
+
+    /// Update an HTTP tool. Fails with `NotFound` if it doesn't exist.
+    async fn update(&self, id: Uuid, input: UpdateHttpTool) -> DbResult<HttpTool>;
+
+    /// Delete an HTTP tool. Fails with `NotFound` if it doesn't exist.
+    async fn delete(&self, id: Uuid) -> DbResult<()>;
+}
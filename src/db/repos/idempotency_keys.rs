@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::{
+    db::error::DbResult,
+    models::{IdempotencyRecord, IdempotentResponse},
+};
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait IdempotencyKeyRepo: Send + Sync {
+    /// Claim `record.key` for a new request, replacing a record that has
+    /// expired. Returns `None` when claimed, otherwise the record that
+    /// already holds the key.
+    async fn claim(&self, record: &IdempotencyRecord) -> DbResult<Option<IdempotencyRecord>>;
+
+    /// Mark the request holding `key` as finished, storing the response to
+    /// replay (`None` when it was too large to store).
+    async fn complete(&self, key: &str, response: Option<&IdempotentResponse>) -> DbResult<()>;
+
+    /// Drop an in-progress claim so the request can be retried. Completed
+    /// records are kept.
+    async fn release(&self, key: &str) -> DbResult<()>;
+
+    /// Delete records that expired before `now`. Returns how many were
+    /// deleted.
+    async fn delete_expired(&self, now: DateTime<Utc>) -> DbResult<u64>;
+}
@@ -0,0 +1,58 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::{ListParams, ListResult};
+use crate::{
+    db::error::DbResult,
+    models::{Invitation, NewInvitation},
+};
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait InvitationRepo: Send + Sync {
+    /// Store a pending invitation.
+    async fn create(&self, input: NewInvitation) -> DbResult<Invitation>;
+
+    /// Get an invitation by ID.
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<Invitation>>;
+
+    /// Get an invitation by the SHA-256 hash of its token.
+    async fn get_by_token_hash(&self, token_hash: &str) -> DbResult<Option<Invitation>>;
+
+    /// List an organization's invitations, newest first by default.
+    async fn list_by_org(
+        &self,
+        org_id: Uuid,
+        params: ListParams,
+    ) -> DbResult<ListResult<Invitation>>;
+
+    /// Pending invitations for `email` (lowercased) that haven't expired at
+    /// `now`, across all organizations.
+    async fn list_pending_by_email(
+        &self,
+        email: &str,
+        now: DateTime<Utc>,
+    ) -> DbResult<Vec<Invitation>>;
+
+    /// Replace a pending invitation's token and expiry, e.g. when it is
+    /// resent. Fails with `NotFound` if it isn't pending.
+    async fn refresh_token(
+        &self,
+        id: Uuid,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> DbResult<Invitation>;
+
+    /// Record a delivery of the invitation.
+    async fn mark_sent(&self, id: Uuid) -> DbResult<()>;
+
+    /// Revoke a pending invitation. Fails with `NotFound` if it isn't
+    /// pending.
+    async fn revoke(&self, id: Uuid) -> DbResult<Invitation>;
+
+    /// Mark a pending invitation as accepted by `user_id`. Returns `false`
+    /// if it was no longer pending, e.g. because a concurrent sign-in
+    /// accepted it first.
+    async fn mark_accepted(&self, id: Uuid, user_id: Uuid) -> DbResult<bool>;
+}
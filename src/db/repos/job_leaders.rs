@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::{db::error::DbResult, models::JobLeader};
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait JobLeaderRepo: Send + Sync {
+    /// Record that `node_id` ran `job` at `now`. `acquired_at` is only reset
+    /// when the job changes hands.
+    async fn record_run(&self, job: &str, node_id: &str, now: DateTime<Utc>) -> DbResult<()>;
+
+    /// All job leaders, ordered by job name.
+    async fn list(&self) -> DbResult<Vec<JobLeader>>;
+}
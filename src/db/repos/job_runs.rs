@@ -0,0 +1,32 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::{
+    db::error::DbResult,
+    models::{CreateJobRun, JobRun},
+};
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait JobRunRepo: Send + Sync {
+    async fn create(&self, input: CreateJobRun) -> DbResult<JobRun>;
+
+    /// Most recent runs of `job`, newest first.
+    async fn list(&self, job: &str, limit: i64) -> DbResult<Vec<JobRun>>;
+
+    /// The most recent run of each job.
+    async fn latest(&self) -> DbResult<Vec<JobRun>>;
+
+    /// Delete all but the `keep` most recent runs of `job`. Returns the
+    /// number of runs deleted.
+    async fn prune(&self, job: &str, keep: i64) -> DbResult<u64>;
+
+    /// Pause scheduled runs of `job`. Pausing a paused job is a no-op.
+    async fn pause(&self, job: &str, now: DateTime<Utc>) -> DbResult<()>;
+
+    /// Resume `job`. Returns false if it wasn't paused.
+    async fn resume(&self, job: &str) -> DbResult<bool>;
+
+    /// Names of all paused jobs.
+    async fn list_paused(&self) -> DbResult<Vec<String>>;
+}
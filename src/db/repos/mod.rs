@@ -1,23 +1,44 @@
+mod admin_passkeys;
+mod admin_totp;
+mod agents;
 mod api_keys;
 mod audit_logs;
+mod client_cert_mappings;
 mod containers;
 mod conversations;
 pub mod cursor;
 #[cfg(feature = "sso")]
 mod domain_verifications;
+mod email_log;
+mod federation;
 mod files;
+mod fine_tuning_jobs;
+mod http_tools;
+mod idempotency_keys;
+mod invitations;
+mod job_leaders;
+mod job_runs;
 #[cfg(feature = "mcp")]
 mod mcp_pending_approvals;
+mod model_catalog_entries;
 mod model_pricing;
 mod oauth_authorization_codes;
 mod org_rbac_policies;
+mod org_request_policies;
 #[cfg(feature = "sso")]
 mod org_sso_configs;
 mod organizations;
 mod projects;
+mod provider_files;
+mod provider_probes;
+mod provider_settings;
+mod provider_usage_imports;
 mod providers;
 mod response_events;
 mod responses;
+mod role_elevations;
+mod scheduled_reports;
+mod scheduled_tasks;
 #[cfg(feature = "sso")]
 mod scim_configs;
 #[cfg(feature = "sso")]
@@ -25,36 +46,60 @@ mod scim_group_mappings;
 #[cfg(feature = "sso")]
 mod scim_user_mappings;
 mod service_accounts;
+mod shadow_results;
 mod skills;
 #[cfg(feature = "sso")]
 mod sso_group_mappings;
 mod teams;
 mod templates;
 mod usage;
+mod usage_anomalies;
 mod users;
+mod vector_store_syncs;
 mod vector_stores;
 
+pub use admin_passkeys::*;
+pub use admin_totp::*;
+pub use agents::*;
 pub use api_keys::*;
 pub use audit_logs::*;
 use chrono::NaiveDate;
+pub use client_cert_mappings::*;
 pub use containers::*;
 pub use conversations::*;
 pub use cursor::*;
 #[cfg(feature = "sso")]
 pub use domain_verifications::*;
+pub use email_log::*;
+pub use federation::*;
 pub use files::*;
+pub use fine_tuning_jobs::*;
+pub use http_tools::*;
+pub use idempotency_keys::*;
+pub use invitations::*;
+pub use job_leaders::*;
+pub use job_runs::*;
 #[cfg(feature = "mcp")]
 pub use mcp_pending_approvals::*;
+pub use model_catalog_entries::*;
 pub use model_pricing::*;
 pub use oauth_authorization_codes::*;
 pub use org_rbac_policies::*;
+pub use org_request_policies::*;
 #[cfg(feature = "sso")]
 pub use org_sso_configs::*;
 pub use organizations::*;
 pub use projects::*;
+pub use provider_files::*;
+pub use provider_probes::*;
+pub use provider_settings::*;
+pub use provider_usage_imports::*;
 pub use providers::*;
 pub use response_events::*;
 pub use responses::*;
+pub use role_elevations::*;
+pub use scheduled_reports::*;
+pub use scheduled_tasks::*;
 #[cfg(feature = "sso")]
 pub use scim_configs::*;
 #[cfg(feature = "sso")]
@@ -62,15 +107,20 @@ pub use scim_group_mappings::*;
 #[cfg(feature = "sso")]
 pub use scim_user_mappings::*;
 pub use service_accounts::*;
+pub use shadow_results::*;
 pub use skills::*;
 #[cfg(feature = "sso")]
 pub use sso_group_mappings::*;
 pub use teams::*;
 pub use templates::*;
 pub use usage::*;
+pub use usage_anomalies::*;
 pub use users::*;
+pub use vector_store_syncs::*;
 pub use vector_stores::*;
 
+use crate::models::LabelSelector;
+
 /// Sort order for list queries.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum SortOrder {
@@ -140,6 +190,10 @@ pub struct ListParams {
     pub sort_order: SortOrder,
     /// Include soft-deleted records in results.
     pub include_deleted: bool,
+    /// Only return records carrying all of these labels. Honoured by the
+    /// list methods of labelled resources (organizations, projects, API
+    /// keys, dynamic providers and templates); ignored elsewhere.
+    pub labels: LabelSelector,
 }
 
 /// Hard upper bound on `ListParams.limit`. A client passing a giant value
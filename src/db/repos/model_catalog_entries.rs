@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    db::error::DbResult,
+    models::{CatalogEntryMetadata, CreateModelCatalogEntry, ModelCatalogEntry},
+};
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait ModelCatalogEntryRepo: Send + Sync {
+    /// Add an entry. Fails with `Conflict` if the provider already has an
+    /// entry for the model.
+    async fn create(&self, input: CreateModelCatalogEntry) -> DbResult<ModelCatalogEntry>;
+
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<ModelCatalogEntry>>;
+
+    /// Every entry, ordered by provider and model.
+    async fn list(&self) -> DbResult<Vec<ModelCatalogEntry>>;
+
+    /// Replace the metadata of an entry. Fails with `NotFound` if it doesn't
+    /// exist.
+    async fn update(&self, id: Uuid, metadata: CatalogEntryMetadata)
+    -> DbResult<ModelCatalogEntry>;
+
+    /// Delete an entry. Returns false if it doesn't exist.
+    async fn delete(&self, id: Uuid) -> DbResult<bool>;
+}
@@ -0,0 +1,38 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    db::error::DbResult,
+    models::{CreateOrgRequestPolicy, OrgRequestPolicy, UpdateOrgRequestPolicy},
+};
+
+/// Repository for per-organization request policies.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait OrgRequestPolicyRepo: Send + Sync {
+    /// Create a policy. Returns `DbError::Conflict` if the org already has a
+    /// policy with the same name.
+    async fn create(
+        &self,
+        org_id: Uuid,
+        input: CreateOrgRequestPolicy,
+    ) -> DbResult<OrgRequestPolicy>;
+
+    /// Get a policy by ID.
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<OrgRequestPolicy>>;
+
+    /// List all policies for an organization, highest priority first.
+    async fn list_by_org(&self, org_id: Uuid) -> DbResult<Vec<OrgRequestPolicy>>;
+
+    /// List enabled policies for an organization, highest priority first.
+    async fn list_enabled_by_org(&self, org_id: Uuid) -> DbResult<Vec<OrgRequestPolicy>>;
+
+    /// Update a policy. Returns `DbError::NotFound` if it doesn't exist.
+    async fn update(&self, id: Uuid, input: UpdateOrgRequestPolicy) -> DbResult<OrgRequestPolicy>;
+
+    /// Delete a policy (hard delete).
+    async fn delete(&self, id: Uuid) -> DbResult<()>;
+
+    /// Count policies for an organization.
+    async fn count_by_org(&self, org_id: Uuid) -> DbResult<i64>;
+}
@@ -3,7 +3,9 @@ use uuid::Uuid;
 
 use crate::{
     db::error::DbResult,
-    models::{CreateOrgSsoConfig, OrgSsoConfig, OrgSsoConfigWithSecret, UpdateOrgSsoConfig},
+    models::{
+        CreateOrgSsoConfig, OrgSsoConfig, OrgSsoConfigWithSecret, SsoRoleRule, UpdateOrgSsoConfig,
+    },
 };
 
 /// Repository for organization SSO configurations.
@@ -72,6 +74,17 @@ pub trait OrgSsoConfigRepo: Send + Sync {
     /// Delete an SSO configuration (hard delete).
     async fn delete(&self, id: Uuid) -> DbResult<()>;
 
+    /// Get the claim-based role rules for an organization's SSO config.
+    ///
+    /// Returns an empty list when the organization has no SSO config or no rules.
+    async fn get_role_rules(&self, org_id: Uuid) -> DbResult<Vec<SsoRoleRule>>;
+
+    /// Replace the claim-based role rules for an organization's SSO config.
+    ///
+    /// # Errors
+    /// Returns `NotFound` if the organization has no SSO config.
+    async fn set_role_rules(&self, org_id: Uuid, rules: &[SsoRoleRule]) -> DbResult<()>;
+
     /// Find SSO config by email domain.
     ///
     /// Used for IdP discovery: when a user enters their email, we look up
@@ -1,10 +1,15 @@
+use std::collections::BTreeMap;
+
 use async_trait::async_trait;
 use uuid::Uuid;
 
 use super::{ListParams, ListResult};
 use crate::{
     db::error::DbResult,
-    models::{CreateOrganization, Organization, UpdateOrganization},
+    models::{
+        CreateOrganization, ModelAccessPolicy, ModelDegradationPolicy, OrgEntitlements,
+        OrgNetworkPolicy, Organization, ParameterGovernance, UpdateOrganization,
+    },
 };
 
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
@@ -16,5 +21,42 @@ pub trait OrganizationRepo: Send + Sync {
     async fn list(&self, params: ListParams) -> DbResult<ListResult<Organization>>;
     async fn count(&self, include_deleted: bool) -> DbResult<i64>;
     async fn update(&self, id: Uuid, input: UpdateOrganization) -> DbResult<Organization>;
+    /// Replace the org's network policy (`None` lifts it).
+    async fn set_network_policy(
+        &self,
+        id: Uuid,
+        policy: Option<&OrgNetworkPolicy>,
+    ) -> DbResult<Organization>;
+    /// Replace the org's parameter governance rules (`None` lifts them).
+    async fn set_parameter_governance(
+        &self,
+        id: Uuid,
+        governance: Option<&ParameterGovernance>,
+    ) -> DbResult<Organization>;
+    /// Replace the org's model access policy (`None` lifts it).
+    async fn set_model_access(
+        &self,
+        id: Uuid,
+        policy: Option<&ModelAccessPolicy>,
+    ) -> DbResult<Organization>;
+    /// Replace the org's model degradation policy (`None` opts the org out).
+    async fn set_model_degradation(
+        &self,
+        id: Uuid,
+        policy: Option<&ModelDegradationPolicy>,
+    ) -> DbResult<Organization>;
+    /// Replace the org's plan and feature overrides (`None` puts it back on
+    /// the default plan).
+    async fn set_entitlements(
+        &self,
+        id: Uuid,
+        entitlements: Option<&OrgEntitlements>,
+    ) -> DbResult<Organization>;
+    /// Replace the org's labels (an empty map removes them).
+    async fn set_labels(
+        &self,
+        id: Uuid,
+        labels: &BTreeMap<String, String>,
+    ) -> DbResult<Organization>;
     async fn delete(&self, id: Uuid) -> DbResult<()>;
 }
@@ -1,10 +1,12 @@
+use std::collections::BTreeMap;
+
 use async_trait::async_trait;
 use uuid::Uuid;
 
 use super::{ListParams, ListResult};
 use crate::{
     db::error::DbResult,
-    models::{CreateProject, Project, UpdateProject},
+    models::{CreateProject, ModelAccessPolicy, ParameterGovernance, Project, UpdateProject},
 };
 
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
@@ -24,5 +26,19 @@ pub trait ProjectRepo: Send + Sync {
     /// a global aggregate without iterating organizations.
     async fn count_total(&self, include_deleted: bool) -> DbResult<i64>;
     async fn update(&self, id: Uuid, input: UpdateProject) -> DbResult<Project>;
+    /// Replace the project's parameter governance rules (`None` lifts them).
+    async fn set_parameter_governance(
+        &self,
+        id: Uuid,
+        governance: Option<&ParameterGovernance>,
+    ) -> DbResult<Project>;
+    /// Replace the project's model access policy (`None` lifts it).
+    async fn set_model_access(
+        &self,
+        id: Uuid,
+        policy: Option<&ModelAccessPolicy>,
+    ) -> DbResult<Project>;
+    /// Replace the project's labels (an empty map removes them).
+    async fn set_labels(&self, id: Uuid, labels: &BTreeMap<String, String>) -> DbResult<Project>;
     async fn delete(&self, id: Uuid) -> DbResult<()>;
 }
@@ -0,0 +1,26 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{db::error::DbResult, models::ProviderFile};
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait ProviderFileRepo: Send + Sync {
+    /// Record a file's copy at a provider. Fails with `Conflict` if the file
+    /// already has a copy there.
+    async fn create(
+        &self,
+        file_id: Uuid,
+        provider: &str,
+        provider_file_id: &str,
+    ) -> DbResult<ProviderFile>;
+
+    /// A file's copy at a provider, if it has one.
+    async fn get(&self, file_id: Uuid, provider: &str) -> DbResult<Option<ProviderFile>>;
+
+    /// All copies of a file, oldest first.
+    async fn list_by_file(&self, file_id: Uuid) -> DbResult<Vec<ProviderFile>>;
+
+    /// Forget a copy. Fails with `NotFound` if it doesn't exist.
+    async fn delete(&self, id: Uuid) -> DbResult<()>;
+}
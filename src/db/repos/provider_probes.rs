@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::{
+    db::error::DbResult,
+    models::{CreateProviderProbeResult, ProbeBucketStats, ProviderProbeResult},
+};
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait ProviderProbeRepo: Send + Sync {
+    /// Record a probe result.
+    async fn create(&self, input: CreateProviderProbeResult) -> DbResult<ProviderProbeResult>;
+
+    /// Aggregate a provider's probe results in `[start, end)` into buckets of
+    /// `bucket_secs` (aligned to the Unix epoch), ordered by bucket then model.
+    async fn history(
+        &self,
+        provider: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        bucket_secs: i64,
+    ) -> DbResult<Vec<ProbeBucketStats>>;
+
+    /// Delete a provider's probe results recorded before `cutoff`.
+    async fn delete_before(&self, provider: &str, cutoff: DateTime<Utc>) -> DbResult<u64>;
+}
@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    db::error::DbResult,
+    models::{ProviderSettings, UpdateProviderSettings},
+};
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait ProviderSettingsRepo: Send + Sync {
+    async fn get(&self, provider_name: &str) -> DbResult<Option<ProviderSettings>>;
+
+    /// Settings of every provider that has them, ordered by provider name.
+    async fn list(&self) -> DbResult<Vec<ProviderSettings>>;
+
+    /// Create or replace the settings of `provider_name`.
+    async fn upsert(
+        &self,
+        provider_name: &str,
+        input: UpdateProviderSettings,
+        updated_by: Option<Uuid>,
+    ) -> DbResult<ProviderSettings>;
+
+    /// Delete the settings of `provider_name`. Returns false if it had none.
+    async fn delete(&self, provider_name: &str) -> DbResult<bool>;
+}
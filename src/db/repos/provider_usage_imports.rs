@@ -0,0 +1,39 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use super::DateRange;
+use crate::{
+    db::error::DbResult,
+    models::{CreateProviderUsageImport, ImportedUsage, ImportedUsageRow, ProviderUsageImport},
+};
+
+/// Storage for provider-reported usage imported for reconciliation.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait ProviderUsageImportRepo: Send + Sync {
+    /// Record an import and its usage rows.
+    ///
+    /// Replaces all imported usage for the provider between the import's
+    /// start and end dates, so re-importing a period never double counts.
+    async fn record_import(
+        &self,
+        input: CreateProviderUsageImport,
+        usage: &[ImportedUsage],
+    ) -> DbResult<ProviderUsageImport>;
+
+    /// Imports, newest first, optionally for a single provider.
+    async fn list_imports(&self, provider: Option<&str>) -> DbResult<Vec<ProviderUsageImport>>;
+
+    /// Delete an import and the usage rows it still holds.
+    ///
+    /// Returns `false` if the import doesn't exist.
+    async fn delete_import(&self, id: Uuid) -> DbResult<bool>;
+
+    /// Imported usage within an inclusive date range, optionally for a single
+    /// provider. Ordered by provider, then date, then model.
+    async fn list_usage(
+        &self,
+        range: DateRange,
+        provider: Option<&str>,
+    ) -> DbResult<Vec<ImportedUsageRow>>;
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use async_trait::async_trait;
 use uuid::Uuid;
 
@@ -66,5 +68,11 @@ pub trait DynamicProviderRepo: Send + Sync {
         params: ListParams,
     ) -> DbResult<ListResult<DynamicProvider>>;
     async fn update(&self, id: Uuid, input: UpdateDynamicProvider) -> DbResult<DynamicProvider>;
+    /// Replace the provider's labels (an empty map removes them).
+    async fn set_labels(
+        &self,
+        id: Uuid,
+        labels: &BTreeMap<String, String>,
+    ) -> DbResult<DynamicProvider>;
     async fn delete(&self, id: Uuid) -> DbResult<()>;
 }
@@ -0,0 +1,62 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::{ListParams, ListResult};
+use crate::{
+    db::error::DbResult,
+    models::{ElevationStatus, NewRoleElevation, RoleElevation},
+};
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait RoleElevationRepo: Send + Sync {
+    /// Store a pending request. Fails with `Conflict` if the member already
+    /// has a pending or active elevation in the organization.
+    async fn create(&self, input: NewRoleElevation) -> DbResult<RoleElevation>;
+
+    /// Get an elevation by ID.
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<RoleElevation>>;
+
+    /// List an organization's elevations, newest first by default.
+    async fn list_by_org(
+        &self,
+        org_id: Uuid,
+        params: ListParams,
+    ) -> DbResult<ListResult<RoleElevation>>;
+
+    /// Mark a pending elevation as active until `expires_at`, remembering
+    /// the role to restore. Fails with `NotFound` if it isn't pending.
+    async fn approve(
+        &self,
+        id: Uuid,
+        decided_by: Option<Uuid>,
+        note: Option<&str>,
+        previous_role: &str,
+        expires_at: DateTime<Utc>,
+    ) -> DbResult<RoleElevation>;
+
+    /// Mark a pending elevation as denied. Fails with `NotFound` if it isn't
+    /// pending.
+    async fn deny(
+        &self,
+        id: Uuid,
+        decided_by: Option<Uuid>,
+        note: Option<&str>,
+    ) -> DbResult<RoleElevation>;
+
+    /// Move an elevation from `from` to the terminal status `to` (revoked or
+    /// expired). Returns `false` if it was no longer in `from`, e.g. because
+    /// another replica ended it first.
+    async fn end(
+        &self,
+        id: Uuid,
+        from: ElevationStatus,
+        to: ElevationStatus,
+        ended_by: Option<Uuid>,
+    ) -> DbResult<bool>;
+
+    /// Active elevations whose `expires_at` is at or before `now`, oldest
+    /// first.
+    async fn list_expired(&self, now: DateTime<Utc>, limit: i64) -> DbResult<Vec<RoleElevation>>;
+}
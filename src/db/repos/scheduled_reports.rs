@@ -0,0 +1,37 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use super::{ListParams, ListResult};
+use crate::{
+    db::error::DbResult,
+    models::{CreateReportRun, ReportRun, ReportRunFilter},
+};
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait ReportRunRepo: Send + Sync {
+    /// Record a report generation/delivery attempt.
+    async fn create(&self, input: CreateReportRun) -> DbResult<ReportRun>;
+
+    /// Get a run by ID, including the rendered report content.
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<ReportRun>>;
+
+    /// List runs newest first. `content` is not loaded.
+    async fn list(
+        &self,
+        filter: ReportRunFilter,
+        params: ListParams,
+    ) -> DbResult<ListResult<ReportRun>>;
+
+    /// All attempts for one report, org and period, oldest first.
+    ///
+    /// The scheduler uses this to decide whether a period still needs to be
+    /// delivered and how many attempts it has used. `content` is not loaded.
+    async fn list_for_period(
+        &self,
+        report_name: &str,
+        org_id: Uuid,
+        period_start: NaiveDate,
+    ) -> DbResult<Vec<ReportRun>>;
+}
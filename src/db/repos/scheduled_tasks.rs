@@ -0,0 +1,67 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{
+    db::error::DbResult,
+    models::{
+        CreateScheduledTask, CreateScheduledTaskRun, ScheduledTask, ScheduledTaskProgress,
+        ScheduledTaskRun, UpdateScheduledTask,
+    },
+};
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait ScheduledTaskRepo: Send + Sync {
+    // ==================== Tasks ====================
+
+    /// Create a task due at `next_run_at`. Fails with `Conflict` if the
+    /// organization already has a task with the same name.
+    async fn create(
+        &self,
+        org_id: Uuid,
+        input: CreateScheduledTask,
+        next_run_at: Option<DateTime<Utc>>,
+    ) -> DbResult<ScheduledTask>;
+
+    async fn get(&self, id: Uuid) -> DbResult<Option<ScheduledTask>>;
+
+    /// An organization's tasks, ordered by name.
+    async fn list_by_org(&self, org_id: Uuid) -> DbResult<Vec<ScheduledTask>>;
+
+    async fn count_by_org(&self, org_id: Uuid) -> DbResult<i64>;
+
+    /// Update a task and set when it next runs. Enabling a task resets its
+    /// failure count. Fails with `NotFound` if it doesn't exist.
+    async fn update(
+        &self,
+        id: Uuid,
+        input: UpdateScheduledTask,
+        next_run_at: Option<DateTime<Utc>>,
+    ) -> DbResult<ScheduledTask>;
+
+    /// Delete a task with its runs. Fails with `NotFound` if it doesn't
+    /// exist.
+    async fn delete(&self, id: Uuid) -> DbResult<()>;
+
+    /// Enabled tasks due at `now`, longest overdue first.
+    async fn list_due(&self, now: DateTime<Utc>, limit: i64) -> DbResult<Vec<ScheduledTask>>;
+
+    // ==================== Runs ====================
+
+    /// Record a finished run and update the task's schedule and failure
+    /// count in one transaction. Fails with `NotFound` if the task no longer
+    /// exists.
+    async fn record_run(
+        &self,
+        run: CreateScheduledTaskRun,
+        progress: ScheduledTaskProgress,
+    ) -> DbResult<ScheduledTaskRun>;
+
+    /// A task's runs, newest first.
+    async fn list_runs(&self, task_id: Uuid, limit: i64) -> DbResult<Vec<ScheduledTaskRun>>;
+
+    /// Delete all but the `keep` newest runs of a task. Returns the number
+    /// of runs deleted.
+    async fn prune_runs(&self, task_id: Uuid, keep: i64) -> DbResult<u64>;
+}
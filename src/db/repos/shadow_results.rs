@@ -0,0 +1,30 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use super::{ListParams, ListResult};
+use crate::{
+    db::error::DbResult,
+    models::{CreateShadowResult, ShadowReportRow, ShadowResult, ShadowResultFilter},
+};
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait ShadowResultRepo: Send + Sync {
+    /// Record a mirrored request.
+    async fn create(&self, input: CreateShadowResult) -> DbResult<ShadowResult>;
+
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<ShadowResult>>;
+
+    /// List results matching `filter`, newest first.
+    async fn list(
+        &self,
+        filter: &ShadowResultFilter,
+        params: ListParams,
+    ) -> DbResult<ListResult<ShadowResult>>;
+
+    /// Aggregate results matching `filter` per rule and provider/model pair.
+    async fn report(&self, filter: &ShadowResultFilter) -> DbResult<Vec<ShadowReportRow>>;
+
+    /// Delete results matching `filter`. Returns the number deleted.
+    async fn delete(&self, filter: &ShadowResultFilter) -> DbResult<u64>;
+}
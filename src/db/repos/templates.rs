@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use async_trait::async_trait;
 use uuid::Uuid;
 
@@ -55,5 +57,7 @@ pub trait TemplateRepo: Send + Sync {
     async fn update(&self, id: Uuid, input: UpdateTemplate) -> DbResult<Template>;
 
     /// Soft-delete a template.
+    /// Replace the template's labels (an empty map removes them).
+    async fn set_labels(&self, id: Uuid, labels: &BTreeMap<String, String>) -> DbResult<Template>;
     async fn delete(&self, id: Uuid) -> DbResult<()>;
 }
@@ -7,9 +7,10 @@ use crate::{
     db::error::DbResult,
     models::{
         DailyModelSpend, DailyOrgSpend, DailyPricingSourceSpend, DailyProjectSpend,
-        DailyProviderSpend, DailySpend, DailyTeamSpend, DailyUserSpend, ModelSpend, OrgSpend,
-        PricingSourceSpend, ProjectSpend, ProviderSpend, RefererSpend, TeamSpend, UsageLogEntry,
-        UsageLogRecord, UsageSummary, UserSpend,
+        DailyProviderAttempts, DailyProviderSpend, DailyReliability, DailySpend, DailyTagSpend,
+        DailyTeamSpend, DailyUserSpend, HourlySpend, ModelSpend, OrgSpend, PricingSourceSpend,
+        ProjectSpend, ProviderSpend, RefererSpend, TagSpend, TeamSpend, UsageLogEntry,
+        UsageLogRecord, UsageMix, UsageSummary, UserSpend,
     },
 };
 
@@ -32,6 +33,8 @@ pub struct UsageLogQuery {
     pub direction: Option<String>,
     /// Filter by record type: "model" or "tool"
     pub record_type: Option<String>,
+    /// Filter by error category (e.g. "rate_limited")
+    pub error_code: Option<String>,
 }
 
 /// Statistics for computing cost forecasts
@@ -543,6 +546,67 @@ pub trait UsageRepo: Send + Sync {
     /// Get daily usage grouped by organization (global).
     async fn get_daily_org_usage_global(&self, range: DateRange) -> DbResult<Vec<DailyOrgSpend>>;
 
+    // ==================== Anomaly Detection Queries ====================
+
+    /// Get spend per API key and organization in hour buckets, over
+    /// `[since, until)`. Hours without usage are omitted.
+    async fn get_hourly_spend(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> DbResult<Vec<HourlySpend>>;
+
+    /// Get model requests per API key, organization, model and referer, over
+    /// `[since, until)`. Tool invocations are excluded.
+    async fn get_usage_mix(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> DbResult<Vec<UsageMix>>;
+
+    // ==================== Reconciliation Queries ====================
+
+    /// Get model requests and provider attempts per day, provider and model.
+    /// Tool invocations are excluded.
+    async fn get_daily_provider_attempts(
+        &self,
+        range: DateRange,
+    ) -> DbResult<Vec<DailyProviderAttempts>>;
+
+    // ==================== Tag Queries ====================
+
+    /// Get usage grouped by the value of tag `key`, optionally limited to one
+    /// organization and/or project. Requests without the tag are grouped
+    /// under `None`. `key` must be a valid tag key.
+    async fn get_tag_usage(
+        &self,
+        key: &str,
+        range: DateRange,
+        org_id: Option<Uuid>,
+        project_id: Option<Uuid>,
+    ) -> DbResult<Vec<TagSpend>>;
+
+    /// Get daily usage grouped by the value of tag `key`.
+    async fn get_daily_tag_usage(
+        &self,
+        key: &str,
+        range: DateRange,
+        org_id: Option<Uuid>,
+        project_id: Option<Uuid>,
+    ) -> DbResult<Vec<DailyTagSpend>>;
+
+    // ==================== Reliability Queries ====================
+
+    /// Get model request outcomes and latency buckets per day, provider,
+    /// model and project, optionally limited to one organization and/or
+    /// project. Tool invocations are excluded.
+    async fn get_daily_reliability(
+        &self,
+        range: DateRange,
+        org_id: Option<Uuid>,
+        project_id: Option<Uuid>,
+    ) -> DbResult<Vec<DailyReliability>>;
+
     // ==================== Individual Log Queries ====================
 
     /// List individual usage log records with optional filtering and cursor pagination.
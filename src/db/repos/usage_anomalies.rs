@@ -0,0 +1,36 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::{ListParams, ListResult};
+use crate::{
+    db::error::DbResult,
+    models::{CreateUsageAnomaly, UsageAnomaly, UsageAnomalyFilter},
+};
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait UsageAnomalyRepo: Send + Sync {
+    /// Record an anomaly.
+    async fn create(&self, input: CreateUsageAnomaly) -> DbResult<UsageAnomaly>;
+
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<UsageAnomaly>>;
+
+    /// List anomalies matching `filter`, newest first.
+    async fn list(
+        &self,
+        filter: &UsageAnomalyFilter,
+        params: ListParams,
+    ) -> DbResult<ListResult<UsageAnomaly>>;
+
+    /// Whether an anomaly with `fingerprint` was recorded at or after `since`.
+    async fn exists_since(&self, fingerprint: &str, since: DateTime<Utc>) -> DbResult<bool>;
+
+    /// Mark an anomaly acknowledged. Returns `None` if it doesn't exist;
+    /// acknowledging twice keeps the first acknowledgement.
+    async fn acknowledge(
+        &self,
+        id: Uuid,
+        acknowledged_by: Option<Uuid>,
+    ) -> DbResult<Option<UsageAnomaly>>;
+}
@@ -45,6 +45,10 @@ pub trait UserRepo: Send + Sync {
         params: ListParams,
     ) -> DbResult<ListResult<User>>;
     async fn count_org_members(&self, org_id: Uuid, include_deleted: bool) -> DbResult<i64>;
+    /// Email addresses of an organization's members holding one of `roles`,
+    /// skipping members without one. Used to address notification emails.
+    async fn list_org_member_emails(&self, org_id: Uuid, roles: &[String])
+    -> DbResult<Vec<String>>;
     /// Count every row in `org_memberships`. Used by access-review summaries.
     async fn count_total_org_memberships(&self) -> DbResult<i64>;
 
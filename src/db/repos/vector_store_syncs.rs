@@ -0,0 +1,67 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use super::{ListParams, ListResult};
+use crate::{
+    db::error::DbResult,
+    models::{
+        CreateVectorStoreSync, VectorStoreSync, VectorStoreSyncCounts, VectorStoreSyncItem,
+        VectorStoreSyncStatus,
+    },
+};
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait VectorStoreSyncRepo: Send + Sync {
+    /// Record the start of a sync run with status `running`.
+    async fn create(&self, input: CreateVectorStoreSync) -> DbResult<VectorStoreSync>;
+
+    /// Record the outcome of a run and set `finished_at`.
+    async fn finish(
+        &self,
+        id: Uuid,
+        status: VectorStoreSyncStatus,
+        counts: &VectorStoreSyncCounts,
+        error: Option<&str>,
+    ) -> DbResult<()>;
+
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<VectorStoreSync>>;
+
+    /// List runs for a vector store, newest first.
+    async fn list(
+        &self,
+        vector_store_id: Uuid,
+        params: ListParams,
+    ) -> DbResult<ListResult<VectorStoreSync>>;
+
+    /// Most recently started run for a source. The scheduler uses this to
+    /// decide whether the source is due.
+    async fn latest_for_source(
+        &self,
+        vector_store_id: Uuid,
+        source_name: &str,
+    ) -> DbResult<Option<VectorStoreSync>>;
+
+    /// Every document currently ingested from a source.
+    async fn list_items(
+        &self,
+        vector_store_id: Uuid,
+        source_name: &str,
+    ) -> DbResult<Vec<VectorStoreSyncItem>>;
+
+    /// Insert or replace the ingested version of a document.
+    async fn upsert_item(
+        &self,
+        vector_store_id: Uuid,
+        source_name: &str,
+        item: &VectorStoreSyncItem,
+    ) -> DbResult<()>;
+
+    /// Forget a document that was removed from the source.
+    async fn delete_item(
+        &self,
+        vector_store_id: Uuid,
+        source_name: &str,
+        source_key: &str,
+    ) -> DbResult<()>;
+}
@@ -95,9 +95,14 @@ pub trait VectorStoresRepo: Send + Sync {
         older_than: DateTime<Utc>,
     ) -> DbResult<Vec<VectorStore>>;
 
-    /// Update vector store's last_active_at timestamp
+    /// Update vector store's last_active_at timestamp and push `expires_at`
+    /// forward per its `expires_after` policy. A no-op on expired stores.
     async fn touch_vector_store(&self, id: Uuid) -> DbResult<()>;
 
+    /// Mark stores whose `expires_at` has passed as `expired`.
+    /// Returns the number of stores expired.
+    async fn expire_vector_stores(&self, now: DateTime<Utc>) -> DbResult<u64>;
+
     // ==================== VectorStore Files CRUD ====================
 
     /// Add a file to a vector store (creates a VectorStoreFile link)
@@ -0,0 +1,127 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use super::{
+    backend::{Pool, Row, RowExt, map_unique_violation, query},
+    common::parse_uuid,
+};
+use crate::{
+    db::{
+        error::DbResult,
+        repos::{AdminPasskeyRepo, truncate_to_millis},
+    },
+    models::{AdminPasskey, CreateAdminPasskey},
+};
+
+pub struct SqliteAdminPasskeyRepo {
+    pool: Pool,
+}
+
+impl SqliteAdminPasskeyRepo {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    fn parse_passkey(row: &Row) -> DbResult<AdminPasskey> {
+        Ok(AdminPasskey {
+            id: parse_uuid(&row.col::<String>("id"))?,
+            user_id: parse_uuid(&row.col::<String>("user_id"))?,
+            credential_id: row.col("credential_id"),
+            name: row.col("name"),
+            credential: row.col("credential"),
+            last_used_at: row.col("last_used_at"),
+            created_at: row.col("created_at"),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl AdminPasskeyRepo for SqliteAdminPasskeyRepo {
+    async fn create(&self, input: CreateAdminPasskey) -> DbResult<AdminPasskey> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+
+        query(
+            r#"
+            INSERT INTO admin_passkeys (
+                id, user_id, credential_id, name, credential, last_used_at, created_at
+            )
+            VALUES (?, ?, ?, ?, ?, NULL, ?)
+            "#,
+        )
+        .bind(id.to_string())
+        .bind(input.user_id.to_string())
+        .bind(&input.credential_id)
+        .bind(&input.name)
+        .bind(&input.credential)
+        .bind(now)
+        .execute(&self.pool)
+        .await
+        .map_err(map_unique_violation("Passkey is already registered"))?;
+
+        Ok(AdminPasskey {
+            id,
+            user_id: input.user_id,
+            credential_id: input.credential_id,
+            name: input.name,
+            credential: input.credential,
+            last_used_at: None,
+            created_at: now,
+        })
+    }
+
+    async fn list_by_user(&self, user_id: Uuid) -> DbResult<Vec<AdminPasskey>> {
+        let rows = query(
+            r#"
+            SELECT id, user_id, credential_id, name, credential, last_used_at, created_at
+            FROM admin_passkeys
+            WHERE user_id = ?
+            ORDER BY created_at ASC, id ASC
+            "#,
+        )
+        .bind(user_id.to_string())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(Self::parse_passkey).collect()
+    }
+
+    async fn get_by_credential_id(&self, credential_id: &str) -> DbResult<Option<AdminPasskey>> {
+        let row = query(
+            r#"
+            SELECT id, user_id, credential_id, name, credential, last_used_at, created_at
+            FROM admin_passkeys
+            WHERE credential_id = ?
+            "#,
+        )
+        .bind(credential_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|r| Self::parse_passkey(&r)).transpose()
+    }
+
+    async fn record_use(&self, id: Uuid, credential: &str) -> DbResult<()> {
+        let now = truncate_to_millis(Utc::now());
+        query("UPDATE admin_passkeys SET credential = ?, last_used_at = ? WHERE id = ?")
+            .bind(credential)
+            .bind(now)
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, user_id: Uuid, id: Uuid) -> DbResult<bool> {
+        let result = query("DELETE FROM admin_passkeys WHERE id = ? AND user_id = ?")
+            .bind(id.to_string())
+            .bind(user_id.to_string())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
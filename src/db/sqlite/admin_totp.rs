@@ -0,0 +1,138 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use super::{
+    backend::{Pool, Row, RowExt, query},
+    common::parse_uuid,
+};
+use crate::{
+    db::{
+        error::DbResult,
+        repos::{AdminTotpRepo, truncate_to_millis},
+    },
+    models::AdminTotpEnrollment,
+};
+
+pub struct SqliteAdminTotpRepo {
+    pool: Pool,
+}
+
+impl SqliteAdminTotpRepo {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    fn parse_enrollment(row: &Row) -> DbResult<AdminTotpEnrollment> {
+        Ok(AdminTotpEnrollment {
+            user_id: parse_uuid(&row.col::<String>("user_id"))?,
+            secret: row.col("secret"),
+            verified_at: row.col("verified_at"),
+            last_used_step: row.col("last_used_step"),
+            created_at: row.col("created_at"),
+            updated_at: row.col("updated_at"),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl AdminTotpRepo for SqliteAdminTotpRepo {
+    async fn get(&self, user_id: Uuid) -> DbResult<Option<AdminTotpEnrollment>> {
+        let row = query(
+            r#"
+            SELECT user_id, secret, verified_at, last_used_step, created_at, updated_at
+            FROM admin_totp_enrollments
+            WHERE user_id = ?
+            "#,
+        )
+        .bind(user_id.to_string())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|r| Self::parse_enrollment(&r)).transpose()
+    }
+
+    async fn upsert_pending(&self, user_id: Uuid, secret: &str) -> DbResult<AdminTotpEnrollment> {
+        let now = truncate_to_millis(Utc::now());
+
+        query(
+            r#"
+            INSERT INTO admin_totp_enrollments (
+                user_id, secret, verified_at, last_used_step, created_at, updated_at
+            )
+            VALUES (?, ?, NULL, NULL, ?, ?)
+            ON CONFLICT (user_id) DO UPDATE SET
+                secret = excluded.secret,
+                verified_at = NULL,
+                last_used_step = NULL,
+                created_at = excluded.created_at,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(user_id.to_string())
+        .bind(secret)
+        .bind(now)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(AdminTotpEnrollment {
+            user_id,
+            secret: secret.to_string(),
+            verified_at: None,
+            last_used_step: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn mark_verified(&self, user_id: Uuid, step: i64) -> DbResult<bool> {
+        let now = truncate_to_millis(Utc::now());
+        let result = query(
+            r#"
+            UPDATE admin_totp_enrollments
+            SET verified_at = ?, last_used_step = ?, updated_at = ?
+            WHERE user_id = ? AND verified_at IS NULL
+            "#,
+        )
+        .bind(now)
+        .bind(step)
+        .bind(now)
+        .bind(user_id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn record_use(&self, user_id: Uuid, step: i64) -> DbResult<bool> {
+        let now = truncate_to_millis(Utc::now());
+        let result = query(
+            r#"
+            UPDATE admin_totp_enrollments
+            SET last_used_step = ?, updated_at = ?
+            WHERE user_id = ?
+              AND verified_at IS NOT NULL
+              AND (last_used_step IS NULL OR last_used_step < ?)
+            "#,
+        )
+        .bind(step)
+        .bind(now)
+        .bind(user_id.to_string())
+        .bind(step)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn delete(&self, user_id: Uuid) -> DbResult<bool> {
+        let result = query("DELETE FROM admin_totp_enrollments WHERE user_id = ?")
+            .bind(user_id.to_string())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
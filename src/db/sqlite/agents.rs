@@ -0,0 +1,401 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use super::{
+    backend::{Pool, Row, RowExt, begin, map_unique_violation, query},
+    common::parse_uuid,
+};
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{AgentRepo, truncate_to_millis},
+    },
+    models::{
+        Agent, AgentRun, AgentRunOutcome, AgentRunStatus, AgentRunStep, CreateAgent,
+        CreateAgentRun, UpdateAgent,
+    },
+};
+
+const AGENT_COLUMNS: &str = "id, org_id, name, description, model, instructions, tools, \
+                             vector_store_ids, max_iterations, created_at, updated_at";
+
+const RUN_COLUMNS: &str = "id, agent_id, org_id, project_id, user_id, api_key_id, \
+                           service_account_id, provider, model, status, input, output_text, \
+                           error, input_tokens, output_tokens, cost_microcents, created_at, \
+                           completed_at";
+
+const STEP_COLUMNS: &str = "id, run_id, sequence, step_type, content, created_at";
+
+pub struct SqliteAgentRepo {
+    pool: Pool,
+}
+
+impl SqliteAgentRepo {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    fn parse_agent(row: &Row) -> DbResult<Agent> {
+        Ok(Agent {
+            id: parse_uuid(&row.col::<String>("id"))?,
+            org_id: parse_uuid(&row.col::<String>("org_id"))?,
+            name: row.col("name"),
+            description: row.col("description"),
+            model: row.col("model"),
+            instructions: row.col("instructions"),
+            tools: serde_json::from_str(&row.col::<String>("tools"))?,
+            vector_store_ids: serde_json::from_str(&row.col::<String>("vector_store_ids"))?,
+            max_iterations: row.col("max_iterations"),
+            created_at: row.col("created_at"),
+            updated_at: row.col("updated_at"),
+        })
+    }
+
+    fn parse_run(row: &Row) -> DbResult<AgentRun> {
+        let optional_uuid = |name: &str| {
+            row.col::<Option<String>>(name)
+                .map(|s| parse_uuid(&s))
+                .transpose()
+        };
+        let status: String = row.col("status");
+        Ok(AgentRun {
+            id: parse_uuid(&row.col::<String>("id"))?,
+            agent_id: parse_uuid(&row.col::<String>("agent_id"))?,
+            org_id: parse_uuid(&row.col::<String>("org_id"))?,
+            project_id: optional_uuid("project_id")?,
+            user_id: optional_uuid("user_id")?,
+            api_key_id: optional_uuid("api_key_id")?,
+            service_account_id: optional_uuid("service_account_id")?,
+            provider: row.col("provider"),
+            model: row.col("model"),
+            status: status.parse().map_err(DbError::Internal)?,
+            input: serde_json::from_str(&row.col::<String>("input"))?,
+            output_text: row.col("output_text"),
+            error: row.col("error"),
+            input_tokens: row.col("input_tokens"),
+            output_tokens: row.col("output_tokens"),
+            cost_microcents: row.col("cost_microcents"),
+            created_at: row.col("created_at"),
+            completed_at: row.col("completed_at"),
+        })
+    }
+
+    fn parse_step(row: &Row) -> DbResult<AgentRunStep> {
+        Ok(AgentRunStep {
+            id: parse_uuid(&row.col::<String>("id"))?,
+            run_id: parse_uuid(&row.col::<String>("run_id"))?,
+            sequence: row.col("sequence"),
+            step_type: row.col("step_type"),
+            content: serde_json::from_str(&row.col::<String>("content"))?,
+            created_at: row.col("created_at"),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl AgentRepo for SqliteAgentRepo {
+    async fn create_agent(&self, org_id: Uuid, input: CreateAgent) -> DbResult<Agent> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+
+        query(
+            r#"
+            INSERT INTO agents (
+                id, org_id, name, description, model, instructions, tools, vector_store_ids,
+                max_iterations, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(id.to_string())
+        .bind(org_id.to_string())
+        .bind(&input.name)
+        .bind(&input.description)
+        .bind(&input.model)
+        .bind(&input.instructions)
+        .bind(serde_json::to_string(&input.tools)?)
+        .bind(serde_json::to_string(&input.vector_store_ids)?)
+        .bind(input.max_iterations)
+        .bind(now)
+        .bind(now)
+        .execute(&self.pool)
+        .await
+        .map_err(map_unique_violation(format!(
+            "Agent '{}' already exists in this organization",
+            input.name
+        )))?;
+
+        Ok(Agent {
+            id,
+            org_id,
+            name: input.name,
+            description: input.description,
+            model: input.model,
+            instructions: input.instructions,
+            tools: input.tools,
+            vector_store_ids: input.vector_store_ids,
+            max_iterations: input.max_iterations,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn get_agent(&self, id: Uuid) -> DbResult<Option<Agent>> {
+        let sql = format!("SELECT {AGENT_COLUMNS} FROM agents WHERE id = ?");
+        let row = query(&sql)
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|r| Self::parse_agent(&r)).transpose()
+    }
+
+    async fn list_agents(&self, org_id: Uuid) -> DbResult<Vec<Agent>> {
+        let sql = format!("SELECT {AGENT_COLUMNS} FROM agents WHERE org_id = ? ORDER BY name ASC");
+        let rows = query(&sql)
+            .bind(org_id.to_string())
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter().map(Self::parse_agent).collect()
+    }
+
+    async fn update_agent(&self, id: Uuid, input: UpdateAgent) -> DbResult<Agent> {
+        let mut agent = self.get_agent(id).await?.ok_or(DbError::NotFound)?;
+        if let Some(name) = input.name {
+            agent.name = name;
+        }
+        if let Some(description) = input.description {
+            agent.description = Some(description);
+        }
+        if let Some(model) = input.model {
+            agent.model = model;
+        }
+        if let Some(instructions) = input.instructions {
+            agent.instructions = Some(instructions);
+        }
+        if let Some(tools) = input.tools {
+            agent.tools = tools;
+        }
+        if let Some(vector_store_ids) = input.vector_store_ids {
+            agent.vector_store_ids = vector_store_ids;
+        }
+        if let Some(max_iterations) = input.max_iterations {
+            agent.max_iterations = Some(max_iterations);
+        }
+        agent.updated_at = truncate_to_millis(Utc::now());
+
+        let result = query(
+            r#"
+            UPDATE agents SET name = ?, description = ?, model = ?, instructions = ?, tools = ?,
+                vector_store_ids = ?, max_iterations = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(&agent.name)
+        .bind(&agent.description)
+        .bind(&agent.model)
+        .bind(&agent.instructions)
+        .bind(serde_json::to_string(&agent.tools)?)
+        .bind(serde_json::to_string(&agent.vector_store_ids)?)
+        .bind(agent.max_iterations)
+        .bind(agent.updated_at)
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await
+        .map_err(map_unique_violation(format!(
+            "Agent '{}' already exists in this organization",
+            agent.name
+        )))?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+        Ok(agent)
+    }
+
+    async fn delete_agent(&self, id: Uuid) -> DbResult<()> {
+        // Runs and steps are deleted explicitly because the test harness
+        // pool doesn't enable `PRAGMA foreign_keys`, so the `ON DELETE
+        // CASCADE` can't be relied on.
+        let mut tx = begin(&self.pool).await?;
+        query(
+            "DELETE FROM agent_run_steps WHERE run_id IN (SELECT id FROM agent_runs WHERE agent_id = ?)",
+        )
+        .bind(id.to_string())
+        .execute(&mut *tx)
+        .await?;
+        query("DELETE FROM agent_runs WHERE agent_id = ?")
+            .bind(id.to_string())
+            .execute(&mut *tx)
+            .await?;
+        let result = query("DELETE FROM agents WHERE id = ?")
+            .bind(id.to_string())
+            .execute(&mut *tx)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn create_run(&self, input: CreateAgentRun) -> DbResult<AgentRun> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+        let status = AgentRunStatus::InProgress;
+
+        query(
+            r#"
+            INSERT INTO agent_runs (
+                id, agent_id, org_id, project_id, user_id, api_key_id, service_account_id,
+                provider, model, status, input, input_tokens, output_tokens, created_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 0, 0, ?)
+            "#,
+        )
+        .bind(id.to_string())
+        .bind(input.agent_id.to_string())
+        .bind(input.org_id.to_string())
+        .bind(input.project_id.map(|id| id.to_string()))
+        .bind(input.user_id.map(|id| id.to_string()))
+        .bind(input.api_key_id.map(|id| id.to_string()))
+        .bind(input.service_account_id.map(|id| id.to_string()))
+        .bind(&input.provider)
+        .bind(&input.model)
+        .bind(status.as_str())
+        .bind(serde_json::to_string(&input.input)?)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(AgentRun {
+            id,
+            agent_id: input.agent_id,
+            org_id: input.org_id,
+            project_id: input.project_id,
+            user_id: input.user_id,
+            api_key_id: input.api_key_id,
+            service_account_id: input.service_account_id,
+            provider: input.provider,
+            model: input.model,
+            status,
+            input: input.input,
+            output_text: None,
+            error: None,
+            input_tokens: 0,
+            output_tokens: 0,
+            cost_microcents: None,
+            created_at: now,
+            completed_at: None,
+        })
+    }
+
+    async fn get_run(&self, id: Uuid) -> DbResult<Option<AgentRun>> {
+        let sql = format!("SELECT {RUN_COLUMNS} FROM agent_runs WHERE id = ?");
+        let row = query(&sql)
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|r| Self::parse_run(&r)).transpose()
+    }
+
+    async fn list_runs(
+        &self,
+        agent_id: Uuid,
+        project_id: Option<Uuid>,
+        limit: i64,
+    ) -> DbResult<Vec<AgentRun>> {
+        let sql = format!(
+            "SELECT {RUN_COLUMNS} FROM agent_runs \
+             WHERE agent_id = ? AND (? IS NULL OR project_id = ?) \
+             ORDER BY created_at DESC, id DESC LIMIT ?"
+        );
+        let project_id = project_id.map(|id| id.to_string());
+        let rows = query(&sql)
+            .bind(agent_id.to_string())
+            .bind(&project_id)
+            .bind(&project_id)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter().map(Self::parse_run).collect()
+    }
+
+    async fn add_step(
+        &self,
+        run_id: Uuid,
+        sequence: i32,
+        step_type: &str,
+        content: &serde_json::Value,
+    ) -> DbResult<AgentRunStep> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+
+        query(
+            r#"
+            INSERT INTO agent_run_steps (id, run_id, sequence, step_type, content, created_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(id.to_string())
+        .bind(run_id.to_string())
+        .bind(sequence)
+        .bind(step_type)
+        .bind(serde_json::to_string(content)?)
+        .bind(now)
+        .execute(&self.pool)
+        .await
+        .map_err(map_unique_violation(format!(
+            "Run '{run_id}' already has step {sequence}"
+        )))?;
+
+        Ok(AgentRunStep {
+            id,
+            run_id,
+            sequence,
+            step_type: step_type.to_string(),
+            content: content.clone(),
+            created_at: now,
+        })
+    }
+
+    async fn list_steps(&self, run_id: Uuid) -> DbResult<Vec<AgentRunStep>> {
+        let sql = format!(
+            "SELECT {STEP_COLUMNS} FROM agent_run_steps WHERE run_id = ? ORDER BY sequence ASC"
+        );
+        let rows = query(&sql)
+            .bind(run_id.to_string())
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter().map(Self::parse_step).collect()
+    }
+
+    async fn finish_run(&self, id: Uuid, outcome: &AgentRunOutcome) -> DbResult<AgentRun> {
+        let now = truncate_to_millis(Utc::now());
+        let sql = format!(
+            "UPDATE agent_runs SET status = ?, output_text = ?, error = ?, input_tokens = ?, \
+             output_tokens = ?, cost_microcents = ?, completed_at = ? WHERE id = ? \
+             RETURNING {RUN_COLUMNS}"
+        );
+        let row = query(&sql)
+            .bind(outcome.status.as_str())
+            .bind(&outcome.output_text)
+            .bind(&outcome.error)
+            .bind(outcome.input_tokens)
+            .bind(outcome.output_tokens)
+            .bind(outcome.cost_microcents)
+            .bind(now)
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or(DbError::NotFound)?;
+
+        Self::parse_run(&row)
+    }
+}
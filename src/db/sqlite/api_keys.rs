@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
@@ -11,7 +13,7 @@ use crate::{
             cursor_from_row, truncate_to_millis,
         },
     },
-    models::{ApiKey, ApiKeyOwner, ApiKeyWithOwner, BudgetPeriod, CreateApiKey},
+    models::{ApiKey, ApiKeyHash, ApiKeyOwner, ApiKeyWithOwner, BudgetPeriod, CreateApiKey},
 };
 
 pub struct SqliteApiKeyRepo {
@@ -103,6 +105,8 @@ impl SqliteApiKeyRepo {
                         "failed to deserialize sovereignty_requirements: {e}"
                     ))
                 })?,
+            labels: serde_json::from_str(&row.col::<String>("labels"))
+                .map_err(|e| DbError::Internal(format!("failed to deserialize labels: {e}")))?,
         })
     }
 
@@ -123,10 +127,11 @@ impl SqliteApiKeyRepo {
             SELECT id, key_prefix, name, owner_type, owner_id, budget_amount, budget_period,
                    expires_at, last_used_at, created_at, revoked_at,
                    scopes, allowed_models, ip_allowlist, rate_limit_rpm, rate_limit_tpm,
-                   rotated_from_key_id, rotation_grace_until, sovereignty_requirements
+                   rotated_from_key_id, rotation_grace_until, sovereignty_requirements, labels
             FROM api_keys
             WHERE owner_type = 'organization' AND owner_id = ?
             AND (created_at, id) {} (?, ?)
+            AND NOT EXISTS (SELECT 1 FROM json_each(?) l WHERE json_extract(labels, '$."' || l.key || '"') IS NOT l.value)
             ORDER BY created_at {}, id {}
             LIMIT ?
             "#,
@@ -137,6 +142,7 @@ impl SqliteApiKeyRepo {
             .bind(org_id.to_string())
             .bind(cursor.created_at)
             .bind(cursor.id.to_string())
+            .bind(params.labels.to_json().to_string())
             .bind(fetch_limit)
             .fetch_all(&self.pool)
             .await?;
@@ -177,10 +183,11 @@ impl SqliteApiKeyRepo {
             SELECT id, key_prefix, name, owner_type, owner_id, budget_amount, budget_period,
                    expires_at, last_used_at, created_at, revoked_at,
                    scopes, allowed_models, ip_allowlist, rate_limit_rpm, rate_limit_tpm,
-                   rotated_from_key_id, rotation_grace_until, sovereignty_requirements
+                   rotated_from_key_id, rotation_grace_until, sovereignty_requirements, labels
             FROM api_keys
             WHERE owner_type = 'project' AND owner_id = ?
             AND (created_at, id) {} (?, ?)
+            AND NOT EXISTS (SELECT 1 FROM json_each(?) l WHERE json_extract(labels, '$."' || l.key || '"') IS NOT l.value)
             ORDER BY created_at {}, id {}
             LIMIT ?
             "#,
@@ -191,6 +198,7 @@ impl SqliteApiKeyRepo {
             .bind(project_id.to_string())
             .bind(cursor.created_at)
             .bind(cursor.id.to_string())
+            .bind(params.labels.to_json().to_string())
             .bind(fetch_limit)
             .fetch_all(&self.pool)
             .await?;
@@ -231,10 +239,11 @@ impl SqliteApiKeyRepo {
             SELECT id, key_prefix, name, owner_type, owner_id, budget_amount, budget_period,
                    expires_at, last_used_at, created_at, revoked_at,
                    scopes, allowed_models, ip_allowlist, rate_limit_rpm, rate_limit_tpm,
-                   rotated_from_key_id, rotation_grace_until, sovereignty_requirements
+                   rotated_from_key_id, rotation_grace_until, sovereignty_requirements, labels
             FROM api_keys
             WHERE owner_type = 'team' AND owner_id = ?
             AND (created_at, id) {} (?, ?)
+            AND NOT EXISTS (SELECT 1 FROM json_each(?) l WHERE json_extract(labels, '$."' || l.key || '"') IS NOT l.value)
             ORDER BY created_at {}, id {}
             LIMIT ?
             "#,
@@ -245,6 +254,7 @@ impl SqliteApiKeyRepo {
             .bind(team_id.to_string())
             .bind(cursor.created_at)
             .bind(cursor.id.to_string())
+            .bind(params.labels.to_json().to_string())
             .bind(fetch_limit)
             .fetch_all(&self.pool)
             .await?;
@@ -285,10 +295,11 @@ impl SqliteApiKeyRepo {
             SELECT id, key_prefix, name, owner_type, owner_id, budget_amount, budget_period,
                    expires_at, last_used_at, created_at, revoked_at,
                    scopes, allowed_models, ip_allowlist, rate_limit_rpm, rate_limit_tpm,
-                   rotated_from_key_id, rotation_grace_until, sovereignty_requirements
+                   rotated_from_key_id, rotation_grace_until, sovereignty_requirements, labels
             FROM api_keys
             WHERE owner_type = 'user' AND owner_id = ?
             AND (created_at, id) {} (?, ?)
+            AND NOT EXISTS (SELECT 1 FROM json_each(?) l WHERE json_extract(labels, '$."' || l.key || '"') IS NOT l.value)
             ORDER BY created_at {}, id {}
             LIMIT ?
             "#,
@@ -299,6 +310,7 @@ impl SqliteApiKeyRepo {
             .bind(user_id.to_string())
             .bind(cursor.created_at)
             .bind(cursor.id.to_string())
+            .bind(params.labels.to_json().to_string())
             .bind(fetch_limit)
             .fetch_all(&self.pool)
             .await?;
@@ -339,10 +351,11 @@ impl SqliteApiKeyRepo {
             SELECT id, key_prefix, name, owner_type, owner_id, budget_amount, budget_period,
                    expires_at, last_used_at, created_at, revoked_at,
                    scopes, allowed_models, ip_allowlist, rate_limit_rpm, rate_limit_tpm,
-                   rotated_from_key_id, rotation_grace_until, sovereignty_requirements
+                   rotated_from_key_id, rotation_grace_until, sovereignty_requirements, labels
             FROM api_keys
             WHERE owner_type = 'service_account' AND owner_id = ?
             AND (created_at, id) {} (?, ?)
+            AND NOT EXISTS (SELECT 1 FROM json_each(?) l WHERE json_extract(labels, '$."' || l.key || '"') IS NOT l.value)
             ORDER BY created_at {}, id {}
             LIMIT ?
             "#,
@@ -353,6 +366,7 @@ impl SqliteApiKeyRepo {
             .bind(service_account_id.to_string())
             .bind(cursor.created_at)
             .bind(cursor.id.to_string())
+            .bind(params.labels.to_json().to_string())
             .bind(fetch_limit)
             .fetch_all(&self.pool)
             .await?;
@@ -380,9 +394,10 @@ impl SqliteApiKeyRepo {
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 impl ApiKeyRepo for SqliteApiKeyRepo {
-    async fn create(&self, input: CreateApiKey, key_hash: &str) -> DbResult<ApiKey> {
+    async fn create_with_hash(&self, input: CreateApiKey, hash: &ApiKeyHash) -> DbResult<ApiKey> {
         let id = Uuid::new_v4();
         let now = truncate_to_millis(chrono::Utc::now());
+        let key_hash = hash.key_hash.as_str();
 
         // Extract first 8 characters of hash as prefix (gw_live_xxx...)
         let key_prefix = if key_hash.len() >= 8 {
@@ -396,19 +411,21 @@ impl ApiKeyRepo for SqliteApiKeyRepo {
         query(
             r#"
             INSERT INTO api_keys (
-                id, name, key_hash, key_prefix, owner_type, owner_id,
+                id, name, key_hash, key_prefix, hash_scheme, key_verifier, owner_type, owner_id,
                 budget_amount, budget_period, expires_at,
                 scopes, allowed_models, ip_allowlist, rate_limit_rpm, rate_limit_tpm,
                 sovereignty_requirements,
                 created_at, updated_at
             )
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(id.to_string())
         .bind(&input.name)
         .bind(key_hash)
         .bind(key_prefix)
+        .bind(hash.scheme.as_str())
+        .bind(&hash.verifier)
         .bind(owner_type)
         .bind(owner_id.to_string())
         .bind(input.budget_limit_cents)
@@ -467,6 +484,7 @@ impl ApiKeyRepo for SqliteApiKeyRepo {
             rotated_from_key_id: None,
             rotation_grace_until: None,
             sovereignty_requirements: input.sovereignty_requirements,
+            labels: Default::default(),
         })
     }
 
@@ -476,7 +494,7 @@ impl ApiKeyRepo for SqliteApiKeyRepo {
             SELECT id, key_prefix, name, owner_type, owner_id, budget_amount, budget_period,
                    expires_at, last_used_at, created_at, revoked_at,
                    scopes, allowed_models, ip_allowlist, rate_limit_rpm, rate_limit_tpm,
-                   rotated_from_key_id, rotation_grace_until, sovereignty_requirements
+                   rotated_from_key_id, rotation_grace_until, sovereignty_requirements, labels
             FROM api_keys
             WHERE id = ?
             "#,
@@ -501,7 +519,8 @@ impl ApiKeyRepo for SqliteApiKeyRepo {
                 k.budget_amount, k.budget_period, k.expires_at, k.last_used_at, k.created_at,
                 k.revoked_at,
                 k.scopes, k.allowed_models, k.ip_allowlist, k.rate_limit_rpm, k.rate_limit_tpm,
-                k.rotated_from_key_id, k.rotation_grace_until, k.sovereignty_requirements,
+                k.rotated_from_key_id, k.rotation_grace_until, k.sovereignty_requirements, k.labels,
+                k.hash_scheme, k.key_verifier,
                 CASE
                     WHEN k.owner_type = 'organization' THEN k.owner_id
                     WHEN k.owner_type = 'team' THEN t.org_id
@@ -552,6 +571,8 @@ impl ApiKeyRepo for SqliteApiKeyRepo {
             user_id: user_id.and_then(|s| Uuid::parse_str(&s).ok()),
             service_account_id: service_account_id.and_then(|s| Uuid::parse_str(&s).ok()),
             service_account_roles,
+            hash_scheme: row.col("hash_scheme"),
+            key_verifier: row.col("key_verifier"),
         }))
     }
 
@@ -572,14 +593,16 @@ impl ApiKeyRepo for SqliteApiKeyRepo {
             SELECT id, key_prefix, name, owner_type, owner_id, budget_amount, budget_period,
                    expires_at, last_used_at, created_at, revoked_at,
                    scopes, allowed_models, ip_allowlist, rate_limit_rpm, rate_limit_tpm,
-                   rotated_from_key_id, rotation_grace_until, sovereignty_requirements
+                   rotated_from_key_id, rotation_grace_until, sovereignty_requirements, labels
             FROM api_keys
             WHERE owner_type = 'organization' AND owner_id = ?
+            AND NOT EXISTS (SELECT 1 FROM json_each(?) l WHERE json_extract(labels, '$."' || l.key || '"') IS NOT l.value)
             ORDER BY created_at DESC, id DESC
             LIMIT ?
             "#,
         )
         .bind(org_id.to_string())
+        .bind(params.labels.to_json().to_string())
         .bind(fetch_limit)
         .fetch_all(&self.pool)
         .await?;
@@ -630,14 +653,16 @@ impl ApiKeyRepo for SqliteApiKeyRepo {
             SELECT id, key_prefix, name, owner_type, owner_id, budget_amount, budget_period,
                    expires_at, last_used_at, created_at, revoked_at,
                    scopes, allowed_models, ip_allowlist, rate_limit_rpm, rate_limit_tpm,
-                   rotated_from_key_id, rotation_grace_until, sovereignty_requirements
+                   rotated_from_key_id, rotation_grace_until, sovereignty_requirements, labels
             FROM api_keys
             WHERE owner_type = 'team' AND owner_id = ?
+            AND NOT EXISTS (SELECT 1 FROM json_each(?) l WHERE json_extract(labels, '$."' || l.key || '"') IS NOT l.value)
             ORDER BY created_at DESC, id DESC
             LIMIT ?
             "#,
         )
         .bind(team_id.to_string())
+        .bind(params.labels.to_json().to_string())
         .bind(fetch_limit)
         .fetch_all(&self.pool)
         .await?;
@@ -688,14 +713,16 @@ impl ApiKeyRepo for SqliteApiKeyRepo {
             SELECT id, key_prefix, name, owner_type, owner_id, budget_amount, budget_period,
                    expires_at, last_used_at, created_at, revoked_at,
                    scopes, allowed_models, ip_allowlist, rate_limit_rpm, rate_limit_tpm,
-                   rotated_from_key_id, rotation_grace_until, sovereignty_requirements
+                   rotated_from_key_id, rotation_grace_until, sovereignty_requirements, labels
             FROM api_keys
             WHERE owner_type = 'project' AND owner_id = ?
+            AND NOT EXISTS (SELECT 1 FROM json_each(?) l WHERE json_extract(labels, '$."' || l.key || '"') IS NOT l.value)
             ORDER BY created_at DESC, id DESC
             LIMIT ?
             "#,
         )
         .bind(project_id.to_string())
+        .bind(params.labels.to_json().to_string())
         .bind(fetch_limit)
         .fetch_all(&self.pool)
         .await?;
@@ -746,14 +773,16 @@ impl ApiKeyRepo for SqliteApiKeyRepo {
             SELECT id, key_prefix, name, owner_type, owner_id, budget_amount, budget_period,
                    expires_at, last_used_at, created_at, revoked_at,
                    scopes, allowed_models, ip_allowlist, rate_limit_rpm, rate_limit_tpm,
-                   rotated_from_key_id, rotation_grace_until, sovereignty_requirements
+                   rotated_from_key_id, rotation_grace_until, sovereignty_requirements, labels
             FROM api_keys
             WHERE owner_type = 'user' AND owner_id = ?
+            AND NOT EXISTS (SELECT 1 FROM json_each(?) l WHERE json_extract(labels, '$."' || l.key || '"') IS NOT l.value)
             ORDER BY created_at DESC, id DESC
             LIMIT ?
             "#,
         )
         .bind(user_id.to_string())
+        .bind(params.labels.to_json().to_string())
         .bind(fetch_limit)
         .fetch_all(&self.pool)
         .await?;
@@ -799,6 +828,34 @@ impl ApiKeyRepo for SqliteApiKeyRepo {
         Ok(row.col::<i64>("count"))
     }
 
+    async fn count_active_by_org(&self, org_id: Uuid) -> DbResult<i64> {
+        let now = truncate_to_millis(Utc::now());
+        let org_id = org_id.to_string();
+        let row = query(
+            r#"
+            SELECT COUNT(*) as count
+            FROM api_keys
+            WHERE revoked_at IS NULL
+              AND (expires_at IS NULL OR expires_at >= ?)
+              AND (
+                (owner_type = 'organization' AND owner_id = ?)
+                OR (owner_type = 'team' AND owner_id IN (SELECT id FROM teams WHERE org_id = ?))
+                OR (owner_type = 'project' AND owner_id IN (SELECT id FROM projects WHERE org_id = ?))
+                OR (owner_type = 'service_account'
+                    AND owner_id IN (SELECT id FROM service_accounts WHERE org_id = ?))
+              )
+            "#,
+        )
+        .bind(now)
+        .bind(&org_id)
+        .bind(&org_id)
+        .bind(&org_id)
+        .bind(&org_id)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(row.col::<i64>("count"))
+    }
+
     async fn revoke(&self, id: Uuid) -> DbResult<()> {
         let now = truncate_to_millis(Utc::now());
         query(
@@ -880,14 +937,16 @@ impl ApiKeyRepo for SqliteApiKeyRepo {
                 id, key_prefix, name, owner_type, owner_id,
                 budget_amount, budget_period, expires_at, last_used_at, created_at, revoked_at,
                 scopes, allowed_models, ip_allowlist, rate_limit_rpm, rate_limit_tpm,
-                rotated_from_key_id, rotation_grace_until, sovereignty_requirements
+                rotated_from_key_id, rotation_grace_until, sovereignty_requirements, labels
             FROM api_keys
             WHERE owner_type = 'service_account' AND owner_id = ?
+            AND NOT EXISTS (SELECT 1 FROM json_each(?) l WHERE json_extract(labels, '$."' || l.key || '"') IS NOT l.value)
             ORDER BY created_at DESC, id DESC
             LIMIT ?
             "#,
         )
         .bind(service_account_id.to_string())
+        .bind(params.labels.to_json().to_string())
         .bind(fetch_limit)
         .fetch_all(&self.pool)
         .await?;
@@ -942,15 +1001,16 @@ impl ApiKeyRepo for SqliteApiKeyRepo {
         Ok(result.rows_affected())
     }
 
-    async fn rotate(
+    async fn rotate_with_hash(
         &self,
         old_key_id: Uuid,
         new_key_input: CreateApiKey,
-        new_key_hash: &str,
+        hash: &ApiKeyHash,
         grace_until: DateTime<Utc>,
     ) -> DbResult<ApiKey> {
         let new_id = Uuid::new_v4();
         let now = truncate_to_millis(Utc::now());
+        let new_key_hash = hash.key_hash.as_str();
 
         // Extract first 8 characters of hash as prefix
         let key_prefix = if new_key_hash.len() >= 8 {
@@ -977,23 +1037,32 @@ impl ApiKeyRepo for SqliteApiKeyRepo {
         .execute(&mut *tx)
         .await?;
 
+        // The replacement keeps the old key's labels
+        let labels: String = query_scalar("SELECT labels FROM api_keys WHERE id = ?")
+            .bind(old_key_id.to_string())
+            .fetch_optional(&mut *tx)
+            .await?
+            .unwrap_or_else(|| "{}".to_string());
+
         // 2. Insert new key with rotated_from_key_id
         query(
             r#"
             INSERT INTO api_keys (
-                id, name, key_hash, key_prefix, owner_type, owner_id,
+                id, name, key_hash, key_prefix, hash_scheme, key_verifier, owner_type, owner_id,
                 budget_amount, budget_period, expires_at,
                 scopes, allowed_models, ip_allowlist, rate_limit_rpm, rate_limit_tpm,
-                sovereignty_requirements, rotated_from_key_id,
+                sovereignty_requirements, rotated_from_key_id, labels,
                 created_at, updated_at
             )
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(new_id.to_string())
         .bind(&new_key_input.name)
         .bind(new_key_hash)
         .bind(key_prefix)
+        .bind(hash.scheme.as_str())
+        .bind(&hash.verifier)
         .bind(owner_type)
         .bind(owner_id.to_string())
         .bind(new_key_input.budget_limit_cents)
@@ -1026,6 +1095,7 @@ impl ApiKeyRepo for SqliteApiKeyRepo {
                 .and_then(|s| serde_json::to_string(s).ok()),
         )
         .bind(old_key_id.to_string())
+        .bind(&labels)
         .bind(now)
         .bind(now)
         .execute(&mut *tx)
@@ -1055,9 +1125,36 @@ impl ApiKeyRepo for SqliteApiKeyRepo {
             rotated_from_key_id: Some(old_key_id),
             rotation_grace_until: None,
             sovereignty_requirements: new_key_input.sovereignty_requirements,
+            labels: serde_json::from_str(&labels)
+                .map_err(|e| DbError::Internal(format!("failed to deserialize labels: {e}")))?,
         })
     }
 
+    async fn set_labels(&self, id: Uuid, labels: &BTreeMap<String, String>) -> DbResult<ApiKey> {
+        let now = truncate_to_millis(chrono::Utc::now());
+        let labels = serde_json::to_string(labels)
+            .map_err(|e| DbError::Internal(format!("failed to serialize labels: {e}")))?;
+
+        let result = query(
+            r#"
+            UPDATE api_keys
+            SET labels = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(labels)
+        .bind(now)
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        self.get_by_id(id).await?.ok_or(DbError::NotFound)
+    }
+
     async fn get_key_hashes_by_service_account(
         &self,
         service_account_id: Uuid,
@@ -1095,13 +1192,37 @@ impl ApiKeyRepo for SqliteApiKeyRepo {
         Ok(hashes)
     }
 
+    async fn update_hash(&self, id: Uuid, current_hash: &str, hash: &ApiKeyHash) -> DbResult<bool> {
+        let now = truncate_to_millis(Utc::now());
+        let result = query(
+            r#"
+            UPDATE api_keys
+            SET key_hash = ?, hash_scheme = ?, key_verifier = ?, updated_at = ?
+            WHERE id = ? AND key_hash = ?
+            "#,
+        )
+        .bind(&hash.key_hash)
+        .bind(hash.scheme.as_str())
+        .bind(&hash.verifier)
+        .bind(now)
+        .bind(id.to_string())
+        .bind(current_hash)
+        .execute(&self.pool)
+        .await
+        .map_err(map_unique_violation(
+            "API key with this hash already exists",
+        ))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     async fn get_by_name_and_org(&self, org_id: Uuid, name: &str) -> DbResult<Option<ApiKey>> {
         let row = query(
             r#"
             SELECT id, key_prefix, name, owner_type, owner_id, budget_amount, budget_period,
                    expires_at, last_used_at, created_at, revoked_at,
                    scopes, allowed_models, ip_allowlist, rate_limit_rpm, rate_limit_tpm,
-                   rotated_from_key_id, rotation_grace_until, sovereignty_requirements
+                   rotated_from_key_id, rotation_grace_until, sovereignty_requirements, labels
             FROM api_keys
             WHERE name = ? AND owner_type = 'organization' AND owner_id = ? AND revoked_at IS NULL
             "#,
@@ -1218,6 +1339,8 @@ mod tests {
                 name TEXT NOT NULL,
                 key_hash TEXT NOT NULL UNIQUE,
                 key_prefix TEXT NOT NULL,
+                hash_scheme TEXT NOT NULL DEFAULT 'sha256',
+                key_verifier TEXT,
                 owner_type TEXT NOT NULL CHECK (owner_type IN ('organization', 'team', 'project', 'user', 'service_account')),
                 owner_id TEXT NOT NULL,
                 budget_amount INTEGER,
@@ -1233,6 +1356,7 @@ mod tests {
                 rotated_from_key_id TEXT REFERENCES api_keys(id) ON DELETE SET NULL,
                 rotation_grace_until TEXT,
                 sovereignty_requirements TEXT,
+                labels TEXT NOT NULL DEFAULT '{}',
                 created_at TEXT NOT NULL DEFAULT (datetime('now')),
                 updated_at TEXT NOT NULL DEFAULT (datetime('now'))
             )
@@ -0,0 +1,204 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use super::{
+    backend::{Pool, Row, RowExt, map_unique_violation, query},
+    common::parse_uuid,
+};
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{
+            ClientCertMappingRepo, CursorDirection, ListParams, ListResult, PageCursors,
+            cursor_from_row, truncate_to_millis,
+        },
+    },
+    models::{CertMatchType, ClientCertMapping, CreateClientCertMapping},
+};
+
+const COLUMNS: &str = "m.id, m.org_id, m.service_account_id, m.match_type, m.match_value, \
+                       m.description, m.created_at";
+
+pub struct SqliteClientCertMappingRepo {
+    pool: Pool,
+}
+
+impl SqliteClientCertMappingRepo {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    fn parse_mapping(row: &Row) -> DbResult<ClientCertMapping> {
+        Ok(ClientCertMapping {
+            id: parse_uuid(&row.col::<String>("id"))?,
+            org_id: parse_uuid(&row.col::<String>("org_id"))?,
+            service_account_id: parse_uuid(&row.col::<String>("service_account_id"))?,
+            match_type: row
+                .col::<String>("match_type")
+                .parse()
+                .map_err(DbError::Internal)?,
+            match_value: row.col("match_value"),
+            description: row.col("description"),
+            created_at: row.col("created_at"),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl ClientCertMappingRepo for SqliteClientCertMappingRepo {
+    async fn create(
+        &self,
+        org_id: Uuid,
+        service_account_id: Uuid,
+        input: CreateClientCertMapping,
+    ) -> DbResult<ClientCertMapping> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+
+        query(
+            r#"
+            INSERT INTO client_cert_mappings (
+                id, org_id, service_account_id, match_type, match_value, description, created_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(id.to_string())
+        .bind(org_id.to_string())
+        .bind(service_account_id.to_string())
+        .bind(input.match_type.as_str())
+        .bind(&input.match_value)
+        .bind(&input.description)
+        .bind(now)
+        .execute(&self.pool)
+        .await
+        .map_err(map_unique_violation(format!(
+            "A client certificate mapping for {} '{}' already exists",
+            input.match_type.as_str(),
+            input.match_value
+        )))?;
+
+        Ok(ClientCertMapping {
+            id,
+            org_id,
+            service_account_id,
+            match_type: input.match_type,
+            match_value: input.match_value,
+            description: input.description,
+            created_at: now,
+        })
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<ClientCertMapping>> {
+        let sql = format!("SELECT {COLUMNS} FROM client_cert_mappings m WHERE m.id = ?");
+        let row = query(&sql)
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|r| Self::parse_mapping(&r)).transpose()
+    }
+
+    async fn list_by_service_account(
+        &self,
+        service_account_id: Uuid,
+        params: ListParams,
+    ) -> DbResult<ListResult<ClientCertMapping>> {
+        let limit = params.limit.unwrap_or(100);
+        let fetch_limit = limit + 1;
+
+        let (comparison, order, should_reverse) =
+            params.sort_order.cursor_query_params(params.direction);
+        let (cursor_clause, order, should_reverse) = if params.cursor.is_some() {
+            (
+                format!("AND (m.created_at, m.id) {} (?, ?)", comparison),
+                order,
+                should_reverse,
+            )
+        } else {
+            (String::new(), params.sort_order.as_sql(), false)
+        };
+
+        let sql = format!(
+            r#"
+            SELECT {COLUMNS}
+            FROM client_cert_mappings m
+            WHERE m.service_account_id = ? {cursor_clause}
+            ORDER BY m.created_at {order}, m.id {order}
+            LIMIT ?
+            "#
+        );
+
+        let mut q = query(&sql).bind(service_account_id.to_string());
+        if let Some(ref cursor) = params.cursor {
+            q = q.bind(cursor.created_at).bind(cursor.id.to_string());
+        }
+        let rows = q.bind(fetch_limit).fetch_all(&self.pool).await?;
+
+        let has_more = rows.len() as i64 > limit;
+        let mut items = rows
+            .iter()
+            .take(limit as usize)
+            .map(Self::parse_mapping)
+            .collect::<DbResult<Vec<_>>>()?;
+
+        if should_reverse {
+            items.reverse();
+        }
+
+        let direction = if params.cursor.is_some() {
+            params.direction
+        } else {
+            CursorDirection::Forward
+        };
+        let cursors =
+            PageCursors::from_items(&items, has_more, direction, params.cursor.as_ref(), |m| {
+                cursor_from_row(m.created_at, m.id)
+            });
+
+        Ok(ListResult::new(items, has_more, cursors))
+    }
+
+    async fn find_by_identifiers(
+        &self,
+        identifiers: &[(CertMatchType, String)],
+    ) -> DbResult<Vec<ClientCertMapping>> {
+        if identifiers.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let conditions = vec!["(m.match_type = ? AND m.match_value = ?)"; identifiers.len()];
+        let sql = format!(
+            r#"
+            SELECT {COLUMNS}
+            FROM client_cert_mappings m
+            JOIN service_accounts sa ON sa.id = m.service_account_id
+            WHERE sa.deleted_at IS NULL AND ({})
+            "#,
+            conditions.join(" OR ")
+        );
+
+        let mut q = query(&sql);
+        for (match_type, value) in identifiers {
+            q = q.bind(match_type.as_str()).bind(value.clone());
+        }
+        let rows = q.fetch_all(&self.pool).await?;
+
+        rows.iter().map(Self::parse_mapping).collect()
+    }
+
+    async fn delete(&self, id: Uuid) -> DbResult<()> {
+        let result = query("DELETE FROM client_cert_mappings WHERE id = ?")
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        Ok(())
+    }
+}
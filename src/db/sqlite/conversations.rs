@@ -3,7 +3,7 @@ use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
 use super::{
-    backend::{Pool, RowExt, query},
+    backend::{Pool, Row, RowExt, query},
     common::parse_uuid,
 };
 use crate::{
@@ -15,8 +15,9 @@ use crate::{
         },
     },
     models::{
-        AppendMessages, Conversation, ConversationOwnerType, ConversationWithProject,
-        CreateConversation, Message, UpdateConversation,
+        AppendMessages, Conversation, ConversationBranch, ConversationOwnerType,
+        ConversationWithProject, CreateConversation, ForkConversation, Message,
+        SetConversationSummary, UpdateConversation,
     },
 };
 
@@ -29,6 +30,23 @@ impl SqliteConversationRepo {
         Self { pool }
     }
 
+    fn parse_branch(row: &Row) -> DbResult<ConversationBranch> {
+        let messages_json: String = row.col("messages");
+        Ok(ConversationBranch {
+            id: parse_uuid(&row.col::<String>("id"))?,
+            conversation_id: parse_uuid(&row.col::<String>("conversation_id"))?,
+            parent_branch_id: row
+                .col::<Option<String>>("parent_branch_id")
+                .map(|s| parse_uuid(&s))
+                .transpose()?,
+            fork_index: row.col("fork_index"),
+            messages: Self::parse_messages(&messages_json)?,
+            is_active: row.col("is_active"),
+            created_at: row.col("created_at"),
+            updated_at: row.col("updated_at"),
+        })
+    }
+
     fn parse_messages(json_str: &str) -> DbResult<Vec<Message>> {
         serde_json::from_str(json_str).map_err(|e| DbError::Internal(e.to_string()))
     }
@@ -68,7 +86,7 @@ impl SqliteConversationRepo {
 
         let sql = format!(
             r#"
-            SELECT id, owner_type, owner_id, title, models, messages, pin_order, created_at, updated_at
+            SELECT id, owner_type, owner_id, title, models, messages, pin_order, summary, created_at, updated_at
             FROM conversations
             WHERE owner_type = ? AND owner_id = ?
             AND (updated_at, id) {} (?, ?)
@@ -107,6 +125,7 @@ impl SqliteConversationRepo {
                     models: Self::parse_models(&models_json)?,
                     messages: Self::parse_messages(&messages_json)?,
                     pin_order: row.col("pin_order"),
+                    summary: row.col("summary"),
                     created_at: row.col("created_at"),
                     updated_at: row.col("updated_at"),
                 })
@@ -168,6 +187,7 @@ impl ConversationRepo for SqliteConversationRepo {
             models: input.models,
             messages: input.messages,
             pin_order: None,
+            summary: None,
             created_at: now,
             updated_at: now,
         })
@@ -176,7 +196,7 @@ impl ConversationRepo for SqliteConversationRepo {
     async fn get_by_id(&self, id: Uuid) -> DbResult<Option<Conversation>> {
         let result = query(
             r#"
-            SELECT id, owner_type, owner_id, title, models, messages, pin_order, created_at, updated_at
+            SELECT id, owner_type, owner_id, title, models, messages, pin_order, summary, created_at, updated_at
             FROM conversations
             WHERE id = ? AND deleted_at IS NULL
             "#,
@@ -201,6 +221,7 @@ impl ConversationRepo for SqliteConversationRepo {
                     models: Self::parse_models(&models_json)?,
                     messages: Self::parse_messages(&messages_json)?,
                     pin_order: row.col("pin_order"),
+                    summary: row.col("summary"),
                     created_at: row.col("created_at"),
                     updated_at: row.col("updated_at"),
                 }))
@@ -212,7 +233,7 @@ impl ConversationRepo for SqliteConversationRepo {
     async fn get_by_id_and_org(&self, id: Uuid, org_id: Uuid) -> DbResult<Option<Conversation>> {
         let result = query(
             r#"
-            SELECT c.id, c.owner_type, c.owner_id, c.title, c.models, c.messages, c.pin_order, c.created_at, c.updated_at
+            SELECT c.id, c.owner_type, c.owner_id, c.title, c.models, c.messages, c.pin_order, c.summary, c.created_at, c.updated_at
             FROM conversations c
             WHERE c.id = ? AND c.deleted_at IS NULL
             AND (
@@ -248,6 +269,7 @@ impl ConversationRepo for SqliteConversationRepo {
                     models: Self::parse_models(&models_json)?,
                     messages: Self::parse_messages(&messages_json)?,
                     pin_order: row.col("pin_order"),
+                    summary: row.col("summary"),
                     created_at: row.col("created_at"),
                     updated_at: row.col("updated_at"),
                 }))
@@ -276,7 +298,7 @@ impl ConversationRepo for SqliteConversationRepo {
         // First page (no cursor provided)
         let sql = if params.include_deleted {
             r#"
-            SELECT id, owner_type, owner_id, title, models, messages, pin_order, created_at, updated_at
+            SELECT id, owner_type, owner_id, title, models, messages, pin_order, summary, created_at, updated_at
             FROM conversations
             WHERE owner_type = ? AND owner_id = ?
             ORDER BY updated_at DESC, id DESC
@@ -284,7 +306,7 @@ impl ConversationRepo for SqliteConversationRepo {
             "#
         } else {
             r#"
-            SELECT id, owner_type, owner_id, title, models, messages, pin_order, created_at, updated_at
+            SELECT id, owner_type, owner_id, title, models, messages, pin_order, summary, created_at, updated_at
             FROM conversations
             WHERE owner_type = ? AND owner_id = ? AND deleted_at IS NULL
             ORDER BY updated_at DESC, id DESC
@@ -318,6 +340,7 @@ impl ConversationRepo for SqliteConversationRepo {
                     models: Self::parse_models(&models_json)?,
                     messages: Self::parse_messages(&messages_json)?,
                     pin_order: row.col("pin_order"),
+                    summary: row.col("summary"),
                     created_at: row.col("created_at"),
                     updated_at: row.col("updated_at"),
                 })
@@ -370,7 +393,7 @@ impl ConversationRepo for SqliteConversationRepo {
             // Read current state within transaction (with write lock held)
             let current_row = query(
                 r#"
-                SELECT id, owner_type, owner_id, title, models, messages, pin_order, created_at, updated_at
+                SELECT id, owner_type, owner_id, title, models, messages, pin_order, summary, created_at, updated_at
                 FROM conversations
                 WHERE id = ? AND deleted_at IS NULL
                 "#,
@@ -389,6 +412,7 @@ impl ConversationRepo for SqliteConversationRepo {
             let current_models_json: String = current_row.col("models");
             let current_messages_json: String = current_row.col("messages");
             let pin_order: Option<i32> = current_row.col("pin_order");
+            let summary: Option<String> = current_row.col("summary");
             let created_at = current_row.col("created_at");
 
             // Determine new owner (if provided) or keep current
@@ -402,6 +426,7 @@ impl ConversationRepo for SqliteConversationRepo {
             let new_models = input
                 .models
                 .unwrap_or_else(|| Self::parse_models(&current_models_json).unwrap_or_default());
+            let messages_replaced = input.messages.is_some();
             let new_messages = input.messages.unwrap_or_else(|| {
                 Self::parse_messages(&current_messages_json).unwrap_or_default()
             });
@@ -431,6 +456,22 @@ impl ConversationRepo for SqliteConversationRepo {
                 return Err(DbError::NotFound);
             }
 
+            // Keep the active branch (if the conversation has been forked) in step
+            if messages_replaced {
+                query(
+                    r#"
+                    UPDATE conversation_branches
+                    SET messages = ?, updated_at = ?
+                    WHERE conversation_id = ? AND is_active = 1
+                    "#,
+                )
+                .bind(&messages_json)
+                .bind(now)
+                .bind(id.to_string())
+                .execute(&mut *conn)
+                .await?;
+            }
+
             Ok(Conversation {
                 id,
                 owner_type: new_owner_type,
@@ -439,6 +480,7 @@ impl ConversationRepo for SqliteConversationRepo {
                 models: new_models,
                 messages: new_messages,
                 pin_order,
+                summary,
                 created_at,
                 updated_at: now,
             })
@@ -508,6 +550,20 @@ impl ConversationRepo for SqliteConversationRepo {
                 return Err(DbError::NotFound);
             }
 
+            // Keep the active branch (if the conversation has been forked) in step
+            query(
+                r#"
+                UPDATE conversation_branches
+                SET messages = ?, updated_at = ?
+                WHERE conversation_id = ? AND is_active = 1
+                "#,
+            )
+            .bind(&messages_json)
+            .bind(now)
+            .bind(id.to_string())
+            .execute(&mut *conn)
+            .await?;
+
             Ok(messages)
         }
         .await;
@@ -575,6 +631,7 @@ impl ConversationRepo for SqliteConversationRepo {
                 c.models,
                 c.messages,
                 c.pin_order,
+                c.summary,
                 c.created_at,
                 c.updated_at,
                 NULL as project_id,
@@ -594,6 +651,7 @@ impl ConversationRepo for SqliteConversationRepo {
                 c.models,
                 c.messages,
                 c.pin_order,
+                c.summary,
                 c.created_at,
                 c.updated_at,
                 p.id as project_id,
@@ -638,6 +696,7 @@ impl ConversationRepo for SqliteConversationRepo {
                         models: Self::parse_models(&models_json)?,
                         messages: Self::parse_messages(&messages_json)?,
                         pin_order: row.col("pin_order"),
+                        summary: row.col("summary"),
                         created_at: row.col("created_at"),
                         updated_at: row.col("updated_at"),
                     },
@@ -662,7 +721,7 @@ impl ConversationRepo for SqliteConversationRepo {
             // Read current state within transaction (with write lock held)
             let current_row = query(
                 r#"
-                SELECT id, owner_type, owner_id, title, models, messages, pin_order, created_at, updated_at
+                SELECT id, owner_type, owner_id, title, models, messages, pin_order, summary, created_at, updated_at
                 FROM conversations
                 WHERE id = ? AND deleted_at IS NULL
                 "#,
@@ -680,6 +739,7 @@ impl ConversationRepo for SqliteConversationRepo {
             let title: String = current_row.col("title");
             let models_json: String = current_row.col("models");
             let messages_json: String = current_row.col("messages");
+            let summary: Option<String> = current_row.col("summary");
             let created_at = current_row.col("created_at");
 
             let update_result = query(
@@ -707,6 +767,7 @@ impl ConversationRepo for SqliteConversationRepo {
                 models: Self::parse_models(&models_json)?,
                 messages: Self::parse_messages(&messages_json)?,
                 pin_order,
+                summary,
                 created_at,
                 updated_at: now,
             })
@@ -726,8 +787,345 @@ impl ConversationRepo for SqliteConversationRepo {
         result
     }
 
+    // ==================== Branching ====================
+
+    async fn list_branches(&self, id: Uuid) -> DbResult<Vec<ConversationBranch>> {
+        let rows = query(
+            r#"
+            SELECT id, conversation_id, parent_branch_id, fork_index, messages, is_active, created_at, updated_at
+            FROM conversation_branches
+            WHERE conversation_id = ?
+            -- The root branch lists first even if forked within the same millisecond
+            ORDER BY created_at ASC, parent_branch_id IS NOT NULL, id ASC
+            "#,
+        )
+        .bind(id.to_string())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(Self::parse_branch).collect()
+    }
+
+    async fn fork(&self, id: Uuid, input: ForkConversation) -> DbResult<ConversationBranch> {
+        let now = truncate_to_millis(chrono::Utc::now());
+
+        // Use IMMEDIATE transaction mode so concurrent forks and appends can't
+        // interleave between reading the history and switching the active branch.
+        let mut conn = self.pool.acquire().await?;
+        query("BEGIN IMMEDIATE").execute(&mut *conn).await?;
+
+        let result = async {
+            let current_row = query(
+                r#"
+                SELECT messages, created_at
+                FROM conversations
+                WHERE id = ? AND deleted_at IS NULL
+                "#,
+            )
+            .bind(id.to_string())
+            .fetch_optional(&mut *conn)
+            .await?
+            .ok_or(DbError::NotFound)?;
+
+            let current_messages_json: String = current_row.col("messages");
+            let conversation_created_at: DateTime<Utc> = current_row.col("created_at");
+            let mut messages = Self::parse_messages(&current_messages_json)?;
+
+            let fork_index = input.message_index as usize;
+            if fork_index >= messages.len() {
+                return Err(DbError::Validation(format!(
+                    "message_index {} is out of range for a conversation with {} messages",
+                    input.message_index,
+                    messages.len()
+                )));
+            }
+
+            let active_row = query(
+                r#"
+                SELECT id FROM conversation_branches
+                WHERE conversation_id = ? AND is_active = 1
+                "#,
+            )
+            .bind(id.to_string())
+            .fetch_optional(&mut *conn)
+            .await?;
+
+            let parent_branch_id = match active_row {
+                Some(row) => {
+                    let parent_id = parse_uuid(&row.col::<String>("id"))?;
+                    query("UPDATE conversation_branches SET is_active = 0 WHERE id = ?")
+                        .bind(parent_id.to_string())
+                        .execute(&mut *conn)
+                        .await?;
+                    parent_id
+                }
+                None => {
+                    // First fork: record the pre-fork history as the root branch.
+                    // It takes the conversation's creation time so it always
+                    // lists first.
+                    let root_id = Uuid::new_v4();
+                    query(
+                        r#"
+                        INSERT INTO conversation_branches (id, conversation_id, parent_branch_id, fork_index, messages, is_active, created_at, updated_at)
+                        VALUES (?, ?, NULL, 0, ?, 0, ?, ?)
+                        "#,
+                    )
+                    .bind(root_id.to_string())
+                    .bind(id.to_string())
+                    .bind(&current_messages_json)
+                    .bind(conversation_created_at)
+                    .bind(now)
+                    .execute(&mut *conn)
+                    .await?;
+                    root_id
+                }
+            };
+
+            messages.truncate(fork_index);
+            messages.extend(input.message);
+            let messages_json =
+                serde_json::to_string(&messages).map_err(|e| DbError::Internal(e.to_string()))?;
+
+            let branch_id = Uuid::new_v4();
+            query(
+                r#"
+                INSERT INTO conversation_branches (id, conversation_id, parent_branch_id, fork_index, messages, is_active, created_at, updated_at)
+                VALUES (?, ?, ?, ?, ?, 1, ?, ?)
+                "#,
+            )
+            .bind(branch_id.to_string())
+            .bind(id.to_string())
+            .bind(parent_branch_id.to_string())
+            .bind(fork_index as i32)
+            .bind(&messages_json)
+            .bind(now)
+            .bind(now)
+            .execute(&mut *conn)
+            .await?;
+
+            query(
+                r#"
+                UPDATE conversations
+                SET messages = ?, updated_at = ?
+                WHERE id = ? AND deleted_at IS NULL
+                "#,
+            )
+            .bind(&messages_json)
+            .bind(now)
+            .bind(id.to_string())
+            .execute(&mut *conn)
+            .await?;
+
+            Ok(ConversationBranch {
+                id: branch_id,
+                conversation_id: id,
+                parent_branch_id: Some(parent_branch_id),
+                fork_index: fork_index as i32,
+                messages,
+                is_active: true,
+                created_at: now,
+                updated_at: now,
+            })
+        }
+        .await;
+
+        // Commit or rollback based on result
+        match &result {
+            Ok(_) => {
+                query("COMMIT").execute(&mut *conn).await?;
+            }
+            Err(_) => {
+                let _ = query("ROLLBACK").execute(&mut *conn).await;
+            }
+        }
+
+        result
+    }
+
+    async fn switch_branch(&self, id: Uuid, branch_id: Uuid) -> DbResult<Conversation> {
+        let now = truncate_to_millis(chrono::Utc::now());
+
+        let mut conn = self.pool.acquire().await?;
+        query("BEGIN IMMEDIATE").execute(&mut *conn).await?;
+
+        let result = async {
+            let current_row = query(
+                r#"
+                SELECT id, owner_type, owner_id, title, models, messages, pin_order, summary, created_at, updated_at
+                FROM conversations
+                WHERE id = ? AND deleted_at IS NULL
+                "#,
+            )
+            .bind(id.to_string())
+            .fetch_optional(&mut *conn)
+            .await?
+            .ok_or(DbError::NotFound)?;
+
+            let branch_row = query(
+                r#"
+                SELECT messages FROM conversation_branches
+                WHERE id = ? AND conversation_id = ?
+                "#,
+            )
+            .bind(branch_id.to_string())
+            .bind(id.to_string())
+            .fetch_optional(&mut *conn)
+            .await?
+            .ok_or(DbError::NotFound)?;
+            let messages_json: String = branch_row.col("messages");
+
+            query(
+                r#"
+                UPDATE conversation_branches
+                SET is_active = 0
+                WHERE conversation_id = ? AND is_active = 1
+                "#,
+            )
+            .bind(id.to_string())
+            .execute(&mut *conn)
+            .await?;
+            query("UPDATE conversation_branches SET is_active = 1 WHERE id = ?")
+                .bind(branch_id.to_string())
+                .execute(&mut *conn)
+                .await?;
+
+            query(
+                r#"
+                UPDATE conversations
+                SET messages = ?, updated_at = ?
+                WHERE id = ? AND deleted_at IS NULL
+                "#,
+            )
+            .bind(&messages_json)
+            .bind(now)
+            .bind(id.to_string())
+            .execute(&mut *conn)
+            .await?;
+
+            let owner_type_str: String = current_row.col("owner_type");
+            let models_json: String = current_row.col("models");
+
+            Ok(Conversation {
+                id,
+                owner_type: owner_type_str
+                    .parse()
+                    .map_err(|e: String| DbError::Internal(e))?,
+                owner_id: parse_uuid(&current_row.col::<String>("owner_id"))?,
+                title: current_row.col("title"),
+                models: Self::parse_models(&models_json)?,
+                messages: Self::parse_messages(&messages_json)?,
+                pin_order: current_row.col("pin_order"),
+                summary: current_row.col("summary"),
+                created_at: current_row.col("created_at"),
+                updated_at: now,
+            })
+        }
+        .await;
+
+        // Commit or rollback based on result
+        match &result {
+            Ok(_) => {
+                query("COMMIT").execute(&mut *conn).await?;
+            }
+            Err(_) => {
+                let _ = query("ROLLBACK").execute(&mut *conn).await;
+            }
+        }
+
+        result
+    }
+
     // ==================== Retention Operations ====================
 
+    async fn list_needing_summary(
+        &self,
+        org_id: Option<Uuid>,
+        min_messages: u32,
+        resummarize_after: u32,
+        limit: i64,
+    ) -> DbResult<Vec<Conversation>> {
+        let owner_clause = if org_id.is_some() {
+            "c.owner_type = 'project' AND c.owner_id IN (SELECT id FROM projects WHERE org_id = ?)"
+        } else {
+            "c.owner_type = 'user'"
+        };
+        let sql = format!(
+            r#"
+            SELECT c.id, c.owner_type, c.owner_id, c.title, c.models, c.messages, c.pin_order, c.summary, c.created_at, c.updated_at
+            FROM conversations c
+            WHERE c.deleted_at IS NULL
+              AND {owner_clause}
+              AND json_array_length(c.messages) >= ?
+              AND (
+                  c.summary IS NULL
+                  OR json_array_length(c.messages) < c.summary_message_count
+                  OR json_array_length(c.messages) - c.summary_message_count >= ?
+              )
+            ORDER BY c.updated_at DESC, c.id DESC
+            LIMIT ?
+            "#
+        );
+
+        let mut q = query(&sql);
+        if let Some(org_id) = org_id {
+            q = q.bind(org_id.to_string());
+        }
+        let rows = q
+            .bind(min_messages as i64)
+            .bind(resummarize_after.max(1) as i64)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                let owner_type_str: String = row.col("owner_type");
+                let models_json: String = row.col("models");
+                let messages_json: String = row.col("messages");
+
+                Ok(Conversation {
+                    id: parse_uuid(&row.col::<String>("id"))?,
+                    owner_type: owner_type_str
+                        .parse()
+                        .map_err(|e: String| DbError::Internal(e))?,
+                    owner_id: parse_uuid(&row.col::<String>("owner_id"))?,
+                    title: row.col("title"),
+                    models: Self::parse_models(&models_json)?,
+                    messages: Self::parse_messages(&messages_json)?,
+                    pin_order: row.col("pin_order"),
+                    summary: row.col("summary"),
+                    created_at: row.col("created_at"),
+                    updated_at: row.col("updated_at"),
+                })
+            })
+            .collect()
+    }
+
+    async fn set_summary(&self, id: Uuid, input: SetConversationSummary) -> DbResult<()> {
+        let result = query(
+            r#"
+            UPDATE conversations
+            SET summary = ?,
+                summary_message_count = ?,
+                title = CASE WHEN ? IS NOT NULL AND title = ? THEN ? ELSE title END
+            WHERE id = ? AND deleted_at IS NULL
+            "#,
+        )
+        .bind(&input.summary)
+        .bind(input.message_count)
+        .bind(&input.title)
+        .bind(&input.expected_title)
+        .bind(&input.title)
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+        Ok(())
+    }
+
     async fn hard_delete_soft_deleted_before(
         &self,
         cutoff: DateTime<Utc>,
@@ -799,6 +1197,8 @@ mod tests {
                 models TEXT NOT NULL DEFAULT '[]',
                 messages TEXT NOT NULL DEFAULT '[]',
                 pin_order INTEGER,
+                summary TEXT,
+                summary_message_count INTEGER NOT NULL DEFAULT 0,
                 created_at TEXT NOT NULL DEFAULT (datetime('now')),
                 updated_at TEXT NOT NULL DEFAULT (datetime('now')),
                 deleted_at TEXT
@@ -809,6 +1209,24 @@ mod tests {
         .await
         .expect("Failed to create conversations table");
 
+        sqlx::query(
+            r#"
+            CREATE TABLE conversation_branches (
+                id TEXT PRIMARY KEY NOT NULL,
+                conversation_id TEXT NOT NULL,
+                parent_branch_id TEXT,
+                fork_index INTEGER NOT NULL DEFAULT 0,
+                messages TEXT NOT NULL DEFAULT '[]',
+                is_active INTEGER NOT NULL DEFAULT 0,
+                created_at TEXT NOT NULL DEFAULT (datetime('now')),
+                updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create conversation_branches table");
+
         pool
     }
 
@@ -816,6 +1234,8 @@ mod tests {
         Message {
             role: role.to_string(),
             content: content.to_string(),
+            annotations: Vec::new(),
+            attachments: Vec::new(),
         }
     }
 
@@ -2089,6 +2509,8 @@ mod tests {
                 models TEXT NOT NULL DEFAULT '[]',
                 messages TEXT NOT NULL DEFAULT '[]',
                 pin_order INTEGER,
+                summary TEXT,
+                summary_message_count INTEGER NOT NULL DEFAULT 0,
                 created_at TEXT NOT NULL DEFAULT (datetime('now')),
                 updated_at TEXT NOT NULL DEFAULT (datetime('now')),
                 deleted_at TEXT
@@ -2099,6 +2521,24 @@ mod tests {
         .await
         .expect("Failed to create conversations table");
 
+        sqlx::query(
+            r#"
+            CREATE TABLE conversation_branches (
+                id TEXT PRIMARY KEY NOT NULL,
+                conversation_id TEXT NOT NULL,
+                parent_branch_id TEXT,
+                fork_index INTEGER NOT NULL DEFAULT 0,
+                messages TEXT NOT NULL DEFAULT '[]',
+                is_active INTEGER NOT NULL DEFAULT 0,
+                created_at TEXT NOT NULL DEFAULT (datetime('now')),
+                updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create conversation_branches table");
+
         sqlx::query(
             r#"
             CREATE TABLE projects (
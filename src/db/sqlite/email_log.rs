@@ -0,0 +1,219 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::{
+    backend::{Pool, Row, RowExt, query},
+    common::parse_uuid,
+};
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{
+            CursorDirection, EmailLogRepo, ListParams, ListResult, PageCursors, cursor_from_row,
+            truncate_to_millis,
+        },
+    },
+    models::{EmailLogEntry, EmailLogFilter, NewEmailLogEntry},
+};
+
+const COLUMNS: &str =
+    "id, org_id, kind, from_address, recipients, subject, status, error, created_at";
+
+pub struct SqliteEmailLogRepo {
+    pool: Pool,
+}
+
+impl SqliteEmailLogRepo {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    fn parse_entry(row: &Row) -> DbResult<EmailLogEntry> {
+        Ok(EmailLogEntry {
+            id: parse_uuid(&row.col::<String>("id"))?,
+            org_id: row
+                .col::<Option<String>>("org_id")
+                .map(|s| parse_uuid(&s))
+                .transpose()?,
+            kind: row
+                .col::<String>("kind")
+                .parse()
+                .map_err(DbError::Internal)?,
+            from_address: row.col("from_address"),
+            recipients: serde_json::from_str(&row.col::<String>("recipients"))?,
+            subject: row.col("subject"),
+            status: row
+                .col::<String>("status")
+                .parse()
+                .map_err(DbError::Internal)?,
+            error: row.col("error"),
+            created_at: row.col("created_at"),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl EmailLogRepo for SqliteEmailLogRepo {
+    async fn create(&self, input: NewEmailLogEntry) -> DbResult<EmailLogEntry> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+
+        query(
+            r#"
+            INSERT INTO email_send_log (
+                id, org_id, kind, from_address, recipients, subject, status, error, created_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(id.to_string())
+        .bind(input.org_id.map(|id| id.to_string()))
+        .bind(input.kind.as_str())
+        .bind(&input.from_address)
+        .bind(serde_json::to_string(&input.recipients)?)
+        .bind(&input.subject)
+        .bind(input.status.as_str())
+        .bind(&input.error)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(EmailLogEntry {
+            id,
+            org_id: input.org_id,
+            kind: input.kind,
+            from_address: input.from_address,
+            recipients: input.recipients,
+            subject: input.subject,
+            status: input.status,
+            error: input.error,
+            created_at: now,
+        })
+    }
+
+    async fn list(
+        &self,
+        filter: EmailLogFilter,
+        params: ListParams,
+    ) -> DbResult<ListResult<EmailLogEntry>> {
+        let limit = params.limit.unwrap_or(100);
+        let fetch_limit = limit + 1;
+
+        let mut conditions = Vec::new();
+        if filter.org_id.is_some() {
+            conditions.push("org_id = ?".to_string());
+        }
+        if filter.kind.is_some() {
+            conditions.push("kind = ?".to_string());
+        }
+        if filter.status.is_some() {
+            conditions.push("status = ?".to_string());
+        }
+
+        let (comparison, order, should_reverse) =
+            params.sort_order.cursor_query_params(params.direction);
+        let (order, should_reverse) = if params.cursor.is_some() {
+            conditions.push(format!("(created_at, id) {} (?, ?)", comparison));
+            (order, should_reverse)
+        } else {
+            (params.sort_order.as_sql(), false)
+        };
+
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+        let sql = format!(
+            r#"
+            SELECT {COLUMNS}
+            FROM email_send_log
+            {where_clause}
+            ORDER BY created_at {order}, id {order}
+            LIMIT ?
+            "#
+        );
+
+        let mut q = query(&sql);
+        if let Some(org_id) = filter.org_id {
+            q = q.bind(org_id.to_string());
+        }
+        if let Some(kind) = filter.kind {
+            q = q.bind(kind.as_str());
+        }
+        if let Some(status) = filter.status {
+            q = q.bind(status.as_str());
+        }
+        if let Some(ref cursor) = params.cursor {
+            q = q.bind(cursor.created_at).bind(cursor.id.to_string());
+        }
+        let rows = q.bind(fetch_limit).fetch_all(&self.pool).await?;
+
+        let has_more = rows.len() as i64 > limit;
+        let mut items = rows
+            .iter()
+            .take(limit as usize)
+            .map(Self::parse_entry)
+            .collect::<DbResult<Vec<_>>>()?;
+
+        if should_reverse {
+            items.reverse();
+        }
+
+        let direction = if params.cursor.is_some() {
+            params.direction
+        } else {
+            CursorDirection::Forward
+        };
+        let cursors =
+            PageCursors::from_items(&items, has_more, direction, params.cursor.as_ref(), |e| {
+                cursor_from_row(e.created_at, e.id)
+            });
+
+        Ok(ListResult::new(items, has_more, cursors))
+    }
+
+    async fn delete_before(
+        &self,
+        cutoff: DateTime<Utc>,
+        batch_size: u32,
+        max_deletes: u64,
+    ) -> DbResult<u64> {
+        let mut total_deleted: u64 = 0;
+
+        loop {
+            if total_deleted >= max_deletes {
+                break;
+            }
+
+            let remaining = max_deletes - total_deleted;
+            let limit = std::cmp::min(batch_size as u64, remaining) as i64;
+
+            let result = query(
+                r#"
+                DELETE FROM email_send_log
+                WHERE id IN (
+                    SELECT id FROM email_send_log
+                    WHERE created_at < ?
+                    LIMIT ?
+                )
+                "#,
+            )
+            .bind(cutoff)
+            .bind(limit)
+            .execute(&self.pool)
+            .await?;
+
+            let rows_deleted = result.rows_affected();
+            total_deleted += rows_deleted;
+
+            if rows_deleted < limit as u64 {
+                break;
+            }
+        }
+
+        Ok(total_deleted)
+    }
+}
@@ -0,0 +1,201 @@
+use std::collections::BTreeSet;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use super::backend::{Pool, Row, RowExt, begin, query};
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{DateRange, FederationRepo, truncate_to_millis},
+    },
+    models::{FederatedGateway, FederatedUsage, FederatedUsageRow, FederationReport},
+};
+
+pub struct SqliteFederationRepo {
+    pool: Pool,
+}
+
+impl SqliteFederationRepo {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    fn parse_gateway(row: &Row) -> DbResult<FederatedGateway> {
+        Ok(FederatedGateway {
+            id: row.col("id"),
+            region: row.col("region"),
+            version: row.col("version"),
+            providers: serde_json::from_str(&row.col::<String>("providers")).map_err(|e| {
+                DbError::Internal(format!("failed to deserialize federated providers: {e}"))
+            })?,
+            first_report_at: row.col("first_report_at"),
+            last_report_at: row.col("last_report_at"),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl FederationRepo for SqliteFederationRepo {
+    async fn record_report(
+        &self,
+        report: &FederationReport,
+        received_at: DateTime<Utc>,
+    ) -> DbResult<()> {
+        let now = truncate_to_millis(received_at);
+        let providers = serde_json::to_string(&report.providers)?;
+
+        let mut tx = begin(&self.pool).await?;
+
+        query(
+            r#"
+            INSERT INTO federated_gateways (
+                id, region, version, providers, first_report_at, last_report_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?)
+            ON CONFLICT (id) DO UPDATE SET
+                region = excluded.region,
+                version = excluded.version,
+                providers = excluded.providers,
+                last_report_at = excluded.last_report_at
+            "#,
+        )
+        .bind(&report.gateway_id)
+        .bind(&report.region)
+        .bind(&report.version)
+        .bind(&providers)
+        .bind(now)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+
+        let dates: BTreeSet<_> = report.usage.iter().map(|u| u.date).collect();
+        for date in dates {
+            query("DELETE FROM federated_usage WHERE gateway_id = ? AND date = ?")
+                .bind(&report.gateway_id)
+                .bind(date)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        for usage in &report.usage {
+            query(
+                r#"
+                INSERT INTO federated_usage (
+                    gateway_id, date, model, total_cost_microcents, input_tokens,
+                    output_tokens, total_tokens, request_count, updated_at
+                )
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                ON CONFLICT (gateway_id, date, model) DO UPDATE SET
+                    total_cost_microcents = excluded.total_cost_microcents,
+                    input_tokens = excluded.input_tokens,
+                    output_tokens = excluded.output_tokens,
+                    total_tokens = excluded.total_tokens,
+                    request_count = excluded.request_count,
+                    updated_at = excluded.updated_at
+                "#,
+            )
+            .bind(&report.gateway_id)
+            .bind(usage.date)
+            .bind(&usage.model)
+            .bind(usage.total_cost_microcents)
+            .bind(usage.input_tokens)
+            .bind(usage.output_tokens)
+            .bind(usage.total_tokens)
+            .bind(usage.request_count)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn list_gateways(&self) -> DbResult<Vec<FederatedGateway>> {
+        let rows = query(
+            r#"
+            SELECT id, region, version, providers, first_report_at, last_report_at
+            FROM federated_gateways
+            ORDER BY id ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(Self::parse_gateway).collect()
+    }
+
+    async fn get_gateway(&self, id: &str) -> DbResult<Option<FederatedGateway>> {
+        let row = query(
+            r#"
+            SELECT id, region, version, providers, first_report_at, last_report_at
+            FROM federated_gateways
+            WHERE id = ?
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(Self::parse_gateway).transpose()
+    }
+
+    async fn delete_gateway(&self, id: &str) -> DbResult<bool> {
+        let mut tx = begin(&self.pool).await?;
+
+        // Usage rows are removed explicitly; the cascade only fires on pools
+        // with `PRAGMA foreign_keys` enabled, which the test harness lacks.
+        query("DELETE FROM federated_usage WHERE gateway_id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        let result = query("DELETE FROM federated_gateways WHERE id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list_usage(
+        &self,
+        range: DateRange,
+        gateway_id: Option<&str>,
+    ) -> DbResult<Vec<FederatedUsageRow>> {
+        let rows = query(
+            r#"
+            SELECT gateway_id, date, model, total_cost_microcents, input_tokens,
+                   output_tokens, total_tokens, request_count
+            FROM federated_usage
+            WHERE date >= ? AND date <= ?
+              AND (? IS NULL OR gateway_id = ?)
+            ORDER BY date ASC, gateway_id ASC, model ASC
+            "#,
+        )
+        .bind(range.start)
+        .bind(range.end)
+        .bind(gateway_id)
+        .bind(gateway_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| FederatedUsageRow {
+                gateway_id: row.col("gateway_id"),
+                usage: FederatedUsage {
+                    date: row.col("date"),
+                    model: row.col("model"),
+                    total_cost_microcents: row.col("total_cost_microcents"),
+                    input_tokens: row.col("input_tokens"),
+                    output_tokens: row.col("output_tokens"),
+                    total_tokens: row.col("total_tokens"),
+                    request_count: row.col("request_count"),
+                },
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,200 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use super::{
+    backend::{Pool, Row, RowExt, query},
+    common::parse_uuid,
+};
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{FineTuningJobRepo, truncate_to_millis},
+    },
+    models::{CreateFineTuningJobRecord, FineTuningJobRecord, FineTuningJobStatus},
+};
+
+const COLUMNS: &str = "id, provider, provider_job_id, org_id, project_id, team_id, user_id, \
+                       api_key_id, service_account_id, model, training_file, validation_file, \
+                       status, fine_tuned_model, trained_tokens, completed_at, created_at, \
+                       updated_at";
+
+/// Provider statuses after which a job never changes again.
+const TERMINAL_STATUSES: &str = "('succeeded', 'failed', 'cancelled')";
+
+pub struct SqliteFineTuningJobRepo {
+    pool: Pool,
+}
+
+impl SqliteFineTuningJobRepo {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    fn parse_job(row: &Row) -> DbResult<FineTuningJobRecord> {
+        let optional_uuid = |name: &str| {
+            row.col::<Option<String>>(name)
+                .map(|s| parse_uuid(&s))
+                .transpose()
+        };
+        Ok(FineTuningJobRecord {
+            id: parse_uuid(&row.col::<String>("id"))?,
+            provider: row.col("provider"),
+            provider_job_id: row.col("provider_job_id"),
+            org_id: parse_uuid(&row.col::<String>("org_id"))?,
+            project_id: optional_uuid("project_id")?,
+            team_id: optional_uuid("team_id")?,
+            user_id: optional_uuid("user_id")?,
+            api_key_id: optional_uuid("api_key_id")?,
+            service_account_id: optional_uuid("service_account_id")?,
+            model: row.col("model"),
+            training_file: row.col("training_file"),
+            validation_file: row.col("validation_file"),
+            status: row.col("status"),
+            fine_tuned_model: row.col("fine_tuned_model"),
+            trained_tokens: row.col("trained_tokens"),
+            completed_at: row.col("completed_at"),
+            created_at: row.col("created_at"),
+            updated_at: row.col("updated_at"),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl FineTuningJobRepo for SqliteFineTuningJobRepo {
+    async fn create(&self, input: CreateFineTuningJobRecord) -> DbResult<FineTuningJobRecord> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+
+        query(
+            r#"
+            INSERT INTO fine_tuning_jobs (
+                id, provider, provider_job_id, org_id, project_id, team_id, user_id,
+                api_key_id, service_account_id, model, training_file, validation_file,
+                status, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(id.to_string())
+        .bind(&input.provider)
+        .bind(&input.provider_job_id)
+        .bind(input.org_id.to_string())
+        .bind(input.project_id.map(|id| id.to_string()))
+        .bind(input.team_id.map(|id| id.to_string()))
+        .bind(input.user_id.map(|id| id.to_string()))
+        .bind(input.api_key_id.map(|id| id.to_string()))
+        .bind(input.service_account_id.map(|id| id.to_string()))
+        .bind(&input.model)
+        .bind(&input.training_file)
+        .bind(&input.validation_file)
+        .bind(&input.status)
+        .bind(now)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(FineTuningJobRecord {
+            id,
+            provider: input.provider,
+            provider_job_id: input.provider_job_id,
+            org_id: input.org_id,
+            project_id: input.project_id,
+            team_id: input.team_id,
+            user_id: input.user_id,
+            api_key_id: input.api_key_id,
+            service_account_id: input.service_account_id,
+            model: input.model,
+            training_file: input.training_file,
+            validation_file: input.validation_file,
+            status: input.status,
+            fine_tuned_model: None,
+            trained_tokens: None,
+            completed_at: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<FineTuningJobRecord>> {
+        let sql = format!("SELECT {COLUMNS} FROM fine_tuning_jobs WHERE id = ?");
+        let row = query(&sql)
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|r| Self::parse_job(&r)).transpose()
+    }
+
+    async fn list(
+        &self,
+        org_id: Uuid,
+        project_id: Option<Uuid>,
+        limit: i64,
+    ) -> DbResult<Vec<FineTuningJobRecord>> {
+        let sql = format!(
+            "SELECT {COLUMNS} FROM fine_tuning_jobs \
+             WHERE org_id = ? AND (? IS NULL OR project_id = ?) \
+             ORDER BY created_at DESC, id DESC LIMIT ?"
+        );
+        let project_id = project_id.map(|id| id.to_string());
+        let rows = query(&sql)
+            .bind(org_id.to_string())
+            .bind(&project_id)
+            .bind(&project_id)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter().map(Self::parse_job).collect()
+    }
+
+    async fn list_incomplete(&self, limit: i64) -> DbResult<Vec<FineTuningJobRecord>> {
+        let sql = format!(
+            "SELECT {COLUMNS} FROM fine_tuning_jobs WHERE completed_at IS NULL \
+             ORDER BY updated_at ASC LIMIT ?"
+        );
+        let rows = query(&sql).bind(limit).fetch_all(&self.pool).await?;
+
+        rows.iter().map(Self::parse_job).collect()
+    }
+
+    async fn update_status(
+        &self,
+        id: Uuid,
+        status: &FineTuningJobStatus,
+    ) -> DbResult<FineTuningJobRecord> {
+        let now = truncate_to_millis(Utc::now());
+        let sql = format!(
+            "UPDATE fine_tuning_jobs SET status = ?, fine_tuned_model = ?, trained_tokens = ?, \
+             updated_at = ? WHERE id = ? RETURNING {COLUMNS}"
+        );
+        let row = query(&sql)
+            .bind(&status.status)
+            .bind(&status.fine_tuned_model)
+            .bind(status.trained_tokens)
+            .bind(now)
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or(DbError::NotFound)?;
+
+        Self::parse_job(&row)
+    }
+
+    async fn mark_completed(&self, id: Uuid) -> DbResult<bool> {
+        let now = truncate_to_millis(Utc::now());
+        let sql = format!(
+            "UPDATE fine_tuning_jobs SET completed_at = ? \
+             WHERE id = ? AND completed_at IS NULL AND status IN {TERMINAL_STATUSES}"
+        );
+        let result = query(&sql)
+            .bind(now)
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
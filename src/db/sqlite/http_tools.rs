@@ -0,0 +1,182 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use super::{
+    backend::{Pool, Row, RowExt, map_unique_violation, query},
+    common::parse_uuid,
+};
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{HttpToolRepo, truncate_to_millis},
+    },
+    models::{CreateHttpTool, HttpTool, UpdateHttpTool},
+};
+
+const COLUMNS: &str = "id, org_id, name, description, url, headers, parameters, timeout_secs, \
+                       max_response_bytes, created_at, updated_at";
+
+pub struct SqliteHttpToolRepo {
+    pool: Pool,
+}
+
+impl SqliteHttpToolRepo {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    fn parse(row: &Row) -> DbResult<HttpTool> {
+        Ok(HttpTool {
+            id: parse_uuid(&row.col::<String>("id"))?,
+            org_id: parse_uuid(&row.col::<String>("org_id"))?,
+            name: row.col("name"),
+            description: row.col("description"),
+            url: row.col("url"),
+            headers: serde_json::from_str(&row.col::<String>("headers"))?,
+            parameters: row
+                .col::<Option<String>>("parameters")
+                .map(|s| serde_json::from_str(&s))
+                .transpose()?,
+            timeout_secs: row.col("timeout_secs"),
+            max_response_bytes: row.col("max_response_bytes"),
+            created_at: row.col("created_at"),
+            updated_at: row.col("updated_at"),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl HttpToolRepo for SqliteHttpToolRepo {
+    async fn create(&self, org_id: Uuid, input: CreateHttpTool) -> DbResult<HttpTool> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+
+        query(
+            r#"
+            INSERT INTO http_tools (
+                id, org_id, name, description, url, headers, parameters, timeout_secs,
+                max_response_bytes, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(id.to_string())
+        .bind(org_id.to_string())
+        .bind(&input.name)
+        .bind(&input.description)
+        .bind(&input.url)
+        .bind(serde_json::to_string(&input.headers)?)
+        .bind(input.parameters.as_ref().map(|p| p.to_string()))
+        .bind(input.timeout_secs)
+        .bind(input.max_response_bytes)
+        .bind(now)
+        .bind(now)
+        .execute(&self.pool)
+        .await
+        .map_err(map_unique_violation(format!(
+            "HTTP tool '{}' already exists in this organization",
+            input.name
+        )))?;
+
+        Ok(HttpTool {
+            id,
+            org_id,
+            name: input.name,
+            description: input.description,
+            url: input.url,
+            headers: input.headers,
+            parameters: input.parameters,
+            timeout_secs: input.timeout_secs,
+            max_response_bytes: input.max_response_bytes,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<HttpTool>> {
+        let sql = format!("SELECT {COLUMNS} FROM http_tools WHERE id = ?");
+        let row = query(&sql)
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|r| Self::parse(&r)).transpose()
+    }
+
+    async fn list_by_org(&self, org_id: Uuid) -> DbResult<Vec<HttpTool>> {
+        let sql = format!("SELECT {COLUMNS} FROM http_tools WHERE org_id = ? ORDER BY name ASC");
+        let rows = query(&sql)
+            .bind(org_id.to_string())
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter().map(Self::parse).collect()
+    }
+
+    async fn update(&self, id: Uuid, input: UpdateHttpTool) -> DbResult<HttpTool> {
+        let mut tool = self.get_by_id(id).await?.ok_or(DbError::NotFound)?;
+        if let Some(name) = input.name {
+            tool.name = name;
+        }
+        if let Some(description) = input.description {
+            tool.description = Some(description);
+        }
+        if let Some(url) = input.url {
+            tool.url = url;
+        }
+        if let Some(headers) = input.headers {
+            tool.headers = headers;
+        }
+        if let Some(parameters) = input.parameters {
+            tool.parameters = Some(parameters);
+        }
+        if let Some(timeout_secs) = input.timeout_secs {
+            tool.timeout_secs = timeout_secs;
+        }
+        if let Some(max_response_bytes) = input.max_response_bytes {
+            tool.max_response_bytes = max_response_bytes;
+        }
+        tool.updated_at = truncate_to_millis(Utc::now());
+
+        let result = query(
+            r#"
+            UPDATE http_tools SET name = ?, description = ?, url = ?, headers = ?,
+                parameters = ?, timeout_secs = ?, max_response_bytes = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(&tool.name)
+        .bind(&tool.description)
+        .bind(&tool.url)
+        .bind(serde_json::to_string(&tool.headers)?)
+        .bind(tool.parameters.as_ref().map(|p| p.to_string()))
+        .bind(tool.timeout_secs)
+        .bind(tool.max_response_bytes)
+        .bind(tool.updated_at)
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await
+        .map_err(map_unique_violation(format!(
+            "HTTP tool '{}' already exists in this organization",
+            tool.name
+        )))?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+        Ok(tool)
+    }
+
+    async fn delete(&self, id: Uuid) -> DbResult<()> {
+        let result = query("DELETE FROM http_tools WHERE id = ?")
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+        Ok(())
+    }
+}
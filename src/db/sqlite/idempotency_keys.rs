@@ -0,0 +1,138 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use super::backend::{Pool, Row, RowExt, query};
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{IdempotencyKeyRepo, truncate_to_millis},
+    },
+    models::{IdempotencyRecord, IdempotencyStatus, IdempotentResponse},
+};
+
+pub struct SqliteIdempotencyKeyRepo {
+    pool: Pool,
+}
+
+impl SqliteIdempotencyKeyRepo {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    fn parse_record(row: &Row) -> DbResult<IdempotencyRecord> {
+        let response = row
+            .col::<Option<i64>>("response_status")
+            .map(|status| IdempotentResponse {
+                status: status as u16,
+                content_type: row.col("response_content_type"),
+                body: row
+                    .col::<Option<Vec<u8>>>("response_body")
+                    .unwrap_or_default(),
+            });
+        Ok(IdempotencyRecord {
+            key: row.col("key_hash"),
+            request_hash: row.col("request_hash"),
+            status: row
+                .col::<String>("status")
+                .parse()
+                .map_err(DbError::Internal)?,
+            response,
+            created_at: row.col("created_at"),
+            expires_at: row.col("expires_at"),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl IdempotencyKeyRepo for SqliteIdempotencyKeyRepo {
+    async fn claim(&self, record: &IdempotencyRecord) -> DbResult<Option<IdempotencyRecord>> {
+        // The holder can release its claim between our insert and select;
+        // a second attempt then claims the key.
+        for _ in 0..2 {
+            let claimed = query(
+                r#"
+                INSERT INTO idempotency_keys (
+                    key_hash, request_hash, status, created_at, expires_at
+                )
+                VALUES (?, ?, ?, ?, ?)
+                ON CONFLICT (key_hash) DO UPDATE SET
+                    request_hash = excluded.request_hash,
+                    status = excluded.status,
+                    response_status = NULL,
+                    response_content_type = NULL,
+                    response_body = NULL,
+                    created_at = excluded.created_at,
+                    expires_at = excluded.expires_at
+                WHERE idempotency_keys.expires_at <= excluded.created_at
+                "#,
+            )
+            .bind(&record.key)
+            .bind(&record.request_hash)
+            .bind(record.status.as_str())
+            .bind(truncate_to_millis(record.created_at))
+            .bind(truncate_to_millis(record.expires_at))
+            .execute(&self.pool)
+            .await?
+            .rows_affected()
+                > 0;
+            if claimed {
+                return Ok(None);
+            }
+
+            let existing = query(
+                r#"
+                SELECT key_hash, request_hash, status, response_status,
+                       response_content_type, response_body, created_at, expires_at
+                FROM idempotency_keys
+                WHERE key_hash = ?
+                "#,
+            )
+            .bind(&record.key)
+            .fetch_optional(&self.pool)
+            .await?;
+            if let Some(row) = existing {
+                return Self::parse_record(&row).map(Some);
+            }
+        }
+
+        Err(DbError::Conflict(
+            "Idempotency key was claimed and released concurrently".to_string(),
+        ))
+    }
+
+    async fn complete(&self, key: &str, response: Option<&IdempotentResponse>) -> DbResult<()> {
+        query(
+            r#"
+            UPDATE idempotency_keys
+            SET status = ?, response_status = ?, response_content_type = ?, response_body = ?
+            WHERE key_hash = ?
+            "#,
+        )
+        .bind(IdempotencyStatus::Completed.as_str())
+        .bind(response.map(|r| i64::from(r.status)))
+        .bind(response.and_then(|r| r.content_type.clone()))
+        .bind(response.map(|r| r.body.clone()))
+        .bind(key)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn release(&self, key: &str) -> DbResult<()> {
+        query("DELETE FROM idempotency_keys WHERE key_hash = ? AND status = ?")
+            .bind(key)
+            .bind(IdempotencyStatus::InProgress.as_str())
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_expired(&self, now: DateTime<Utc>) -> DbResult<u64> {
+        let result = query("DELETE FROM idempotency_keys WHERE expires_at <= ?")
+            .bind(truncate_to_millis(now))
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
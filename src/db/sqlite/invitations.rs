@@ -0,0 +1,299 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::{
+    backend::{Pool, Row, RowExt, map_unique_violation, query},
+    common::parse_uuid,
+};
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{
+            CursorDirection, InvitationRepo, ListParams, ListResult, PageCursors, cursor_from_row,
+            truncate_to_millis,
+        },
+    },
+    models::{Invitation, InvitationStatus, NewInvitation},
+};
+
+const COLUMNS: &str = "id, org_id, team_id, email, role, status, invited_by, expires_at, \
+                       send_count, last_sent_at, accepted_at, accepted_by, revoked_at, \
+                       created_at, updated_at";
+
+pub struct SqliteInvitationRepo {
+    pool: Pool,
+}
+
+impl SqliteInvitationRepo {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    fn parse_invitation(row: &Row) -> DbResult<Invitation> {
+        let parse_optional_uuid = |col: &str| {
+            row.col::<Option<String>>(col)
+                .map(|s| parse_uuid(&s))
+                .transpose()
+        };
+        let expires_at: DateTime<Utc> = row.col("expires_at");
+
+        Ok(Invitation {
+            id: parse_uuid(&row.col::<String>("id"))?,
+            org_id: parse_uuid(&row.col::<String>("org_id"))?,
+            team_id: parse_optional_uuid("team_id")?,
+            email: row.col("email"),
+            role: row.col("role"),
+            status: InvitationStatus::from_stored(
+                &row.col::<String>("status"),
+                expires_at,
+                Utc::now(),
+            )
+            .map_err(DbError::Internal)?,
+            invited_by: parse_optional_uuid("invited_by")?,
+            expires_at,
+            send_count: row.col("send_count"),
+            last_sent_at: row.col("last_sent_at"),
+            accepted_at: row.col("accepted_at"),
+            accepted_by: parse_optional_uuid("accepted_by")?,
+            revoked_at: row.col("revoked_at"),
+            created_at: row.col("created_at"),
+            updated_at: row.col("updated_at"),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl InvitationRepo for SqliteInvitationRepo {
+    async fn create(&self, input: NewInvitation) -> DbResult<Invitation> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+        let expires_at = truncate_to_millis(input.expires_at);
+
+        query(
+            r#"
+            INSERT INTO invitations (
+                id, org_id, team_id, email, role, token_hash, status, invited_by,
+                expires_at, send_count, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, 'pending', ?, ?, 0, ?, ?)
+            "#,
+        )
+        .bind(id.to_string())
+        .bind(input.org_id.to_string())
+        .bind(input.team_id.map(|id| id.to_string()))
+        .bind(&input.email)
+        .bind(&input.role)
+        .bind(&input.token_hash)
+        .bind(input.invited_by.map(|id| id.to_string()))
+        .bind(expires_at)
+        .bind(now)
+        .bind(now)
+        .execute(&self.pool)
+        .await
+        .map_err(map_unique_violation("Invitation token already exists"))?;
+
+        Ok(Invitation {
+            id,
+            org_id: input.org_id,
+            team_id: input.team_id,
+            email: input.email,
+            role: input.role,
+            status: InvitationStatus::Pending,
+            invited_by: input.invited_by,
+            expires_at,
+            send_count: 0,
+            last_sent_at: None,
+            accepted_at: None,
+            accepted_by: None,
+            revoked_at: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<Invitation>> {
+        let sql = format!("SELECT {COLUMNS} FROM invitations WHERE id = ?");
+        let row = query(&sql)
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|r| Self::parse_invitation(&r)).transpose()
+    }
+
+    async fn get_by_token_hash(&self, token_hash: &str) -> DbResult<Option<Invitation>> {
+        let sql = format!("SELECT {COLUMNS} FROM invitations WHERE token_hash = ?");
+        let row = query(&sql)
+            .bind(token_hash)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|r| Self::parse_invitation(&r)).transpose()
+    }
+
+    async fn list_by_org(
+        &self,
+        org_id: Uuid,
+        params: ListParams,
+    ) -> DbResult<ListResult<Invitation>> {
+        let limit = params.limit.unwrap_or(100);
+        let fetch_limit = limit + 1;
+
+        let (comparison, order, should_reverse) =
+            params.sort_order.cursor_query_params(params.direction);
+        let (cursor_clause, order, should_reverse) = if params.cursor.is_some() {
+            (
+                format!("AND (created_at, id) {} (?, ?)", comparison),
+                order,
+                should_reverse,
+            )
+        } else {
+            (String::new(), params.sort_order.as_sql(), false)
+        };
+
+        let sql = format!(
+            r#"
+            SELECT {COLUMNS}
+            FROM invitations
+            WHERE org_id = ? {cursor_clause}
+            ORDER BY created_at {order}, id {order}
+            LIMIT ?
+            "#
+        );
+
+        let mut q = query(&sql).bind(org_id.to_string());
+        if let Some(ref cursor) = params.cursor {
+            q = q.bind(cursor.created_at).bind(cursor.id.to_string());
+        }
+        let rows = q.bind(fetch_limit).fetch_all(&self.pool).await?;
+
+        let has_more = rows.len() as i64 > limit;
+        let mut items = rows
+            .iter()
+            .take(limit as usize)
+            .map(Self::parse_invitation)
+            .collect::<DbResult<Vec<_>>>()?;
+
+        if should_reverse {
+            items.reverse();
+        }
+
+        let direction = if params.cursor.is_some() {
+            params.direction
+        } else {
+            CursorDirection::Forward
+        };
+        let cursors =
+            PageCursors::from_items(&items, has_more, direction, params.cursor.as_ref(), |i| {
+                cursor_from_row(i.created_at, i.id)
+            });
+
+        Ok(ListResult::new(items, has_more, cursors))
+    }
+
+    async fn list_pending_by_email(
+        &self,
+        email: &str,
+        now: DateTime<Utc>,
+    ) -> DbResult<Vec<Invitation>> {
+        let sql = format!(
+            "SELECT {COLUMNS} FROM invitations \
+             WHERE email = ? AND status = 'pending' AND expires_at > ? \
+             ORDER BY created_at ASC, id ASC"
+        );
+        let rows = query(&sql)
+            .bind(email)
+            .bind(now)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter().map(Self::parse_invitation).collect()
+    }
+
+    async fn refresh_token(
+        &self,
+        id: Uuid,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> DbResult<Invitation> {
+        let result = query(
+            r#"
+            UPDATE invitations SET token_hash = ?, expires_at = ?, updated_at = ?
+            WHERE id = ? AND status = 'pending'
+            "#,
+        )
+        .bind(token_hash)
+        .bind(truncate_to_millis(expires_at))
+        .bind(truncate_to_millis(Utc::now()))
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await
+        .map_err(map_unique_violation("Invitation token already exists"))?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+        self.get_by_id(id).await?.ok_or(DbError::NotFound)
+    }
+
+    async fn mark_sent(&self, id: Uuid) -> DbResult<()> {
+        let now = truncate_to_millis(Utc::now());
+        let result = query(
+            r#"
+            UPDATE invitations SET send_count = send_count + 1, last_sent_at = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(now)
+        .bind(now)
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+        Ok(())
+    }
+
+    async fn revoke(&self, id: Uuid) -> DbResult<Invitation> {
+        let now = truncate_to_millis(Utc::now());
+        let result = query(
+            r#"
+            UPDATE invitations SET status = 'revoked', revoked_at = ?, updated_at = ?
+            WHERE id = ? AND status = 'pending'
+            "#,
+        )
+        .bind(now)
+        .bind(now)
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+        self.get_by_id(id).await?.ok_or(DbError::NotFound)
+    }
+
+    async fn mark_accepted(&self, id: Uuid, user_id: Uuid) -> DbResult<bool> {
+        let now = truncate_to_millis(Utc::now());
+        let result = query(
+            r#"
+            UPDATE invitations SET status = 'accepted', accepted_at = ?, accepted_by = ?,
+                updated_at = ?
+            WHERE id = ? AND status = 'pending'
+            "#,
+        )
+        .bind(now)
+        .bind(user_id.to_string())
+        .bind(now)
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,69 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use super::backend::{Pool, RowExt, query};
+use crate::{
+    db::{
+        error::DbResult,
+        repos::{JobLeaderRepo, truncate_to_millis},
+    },
+    models::JobLeader,
+};
+
+pub struct SqliteJobLeaderRepo {
+    pool: Pool,
+}
+
+impl SqliteJobLeaderRepo {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl JobLeaderRepo for SqliteJobLeaderRepo {
+    async fn record_run(&self, job: &str, node_id: &str, now: DateTime<Utc>) -> DbResult<()> {
+        let now = truncate_to_millis(now);
+
+        query(
+            r#"
+            INSERT INTO job_leaders (job, node_id, acquired_at, last_run_at)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT (job) DO UPDATE SET
+                acquired_at = CASE
+                    WHEN job_leaders.node_id = excluded.node_id THEN job_leaders.acquired_at
+                    ELSE excluded.acquired_at
+                END,
+                node_id = excluded.node_id,
+                last_run_at = excluded.last_run_at
+            "#,
+        )
+        .bind(job)
+        .bind(node_id)
+        .bind(now)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn list(&self) -> DbResult<Vec<JobLeader>> {
+        let rows = query(
+            "SELECT job, node_id, acquired_at, last_run_at FROM job_leaders ORDER BY job ASC",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| JobLeader {
+                job: row.col("job"),
+                node_id: row.col("node_id"),
+                acquired_at: row.col("acquired_at"),
+                last_run_at: row.col("last_run_at"),
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,176 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::{
+    backend::{Pool, Row, RowExt, query},
+    common::parse_uuid,
+};
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{JobRunRepo, truncate_to_millis},
+    },
+    models::{CreateJobRun, JobRun},
+};
+
+const COLUMNS: &str = "id, job, node_id, triggered_by, status, summary, error, started_at, \
+                       finished_at, duration_ms";
+
+pub struct SqliteJobRunRepo {
+    pool: Pool,
+}
+
+impl SqliteJobRunRepo {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    fn parse_run(row: &Row) -> DbResult<JobRun> {
+        Ok(JobRun {
+            id: parse_uuid(&row.col::<String>("id"))?,
+            job: row.col("job"),
+            node_id: row.col("node_id"),
+            triggered_by: row
+                .col::<String>("triggered_by")
+                .parse()
+                .map_err(DbError::Internal)?,
+            status: row
+                .col::<String>("status")
+                .parse()
+                .map_err(DbError::Internal)?,
+            summary: row.col("summary"),
+            error: row.col("error"),
+            started_at: row.col("started_at"),
+            finished_at: row.col("finished_at"),
+            duration_ms: row.col("duration_ms"),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl JobRunRepo for SqliteJobRunRepo {
+    async fn create(&self, input: CreateJobRun) -> DbResult<JobRun> {
+        let id = Uuid::new_v4();
+        let started_at = truncate_to_millis(input.started_at);
+        let finished_at = truncate_to_millis(input.finished_at);
+        let duration_ms = (finished_at - started_at).num_milliseconds().max(0);
+
+        query(
+            r#"
+            INSERT INTO job_runs (
+                id, job, node_id, triggered_by, status, summary, error,
+                started_at, finished_at, duration_ms
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(id.to_string())
+        .bind(&input.job)
+        .bind(&input.node_id)
+        .bind(input.triggered_by.as_str())
+        .bind(input.status.as_str())
+        .bind(&input.summary)
+        .bind(&input.error)
+        .bind(started_at)
+        .bind(finished_at)
+        .bind(duration_ms)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(JobRun {
+            id,
+            job: input.job,
+            node_id: input.node_id,
+            triggered_by: input.triggered_by,
+            status: input.status,
+            summary: input.summary,
+            error: input.error,
+            started_at,
+            finished_at,
+            duration_ms,
+        })
+    }
+
+    async fn list(&self, job: &str, limit: i64) -> DbResult<Vec<JobRun>> {
+        let sql = format!(
+            "SELECT {COLUMNS} FROM job_runs WHERE job = ? ORDER BY started_at DESC, id DESC LIMIT ?"
+        );
+        let rows = query(&sql)
+            .bind(job)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter().map(Self::parse_run).collect()
+    }
+
+    async fn latest(&self) -> DbResult<Vec<JobRun>> {
+        let sql = format!(
+            r#"
+            SELECT {COLUMNS} FROM job_runs r
+            WHERE id = (
+                SELECT id FROM job_runs
+                WHERE job = r.job
+                ORDER BY started_at DESC, id DESC
+                LIMIT 1
+            )
+            ORDER BY job ASC
+            "#
+        );
+        let rows = query(&sql).fetch_all(&self.pool).await?;
+
+        rows.iter().map(Self::parse_run).collect()
+    }
+
+    async fn prune(&self, job: &str, keep: i64) -> DbResult<u64> {
+        let result = query(
+            r#"
+            DELETE FROM job_runs
+            WHERE job = ? AND id NOT IN (
+                SELECT id FROM job_runs
+                WHERE job = ?
+                ORDER BY started_at DESC, id DESC
+                LIMIT ?
+            )
+            "#,
+        )
+        .bind(job)
+        .bind(job)
+        .bind(keep)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn pause(&self, job: &str, now: DateTime<Utc>) -> DbResult<()> {
+        query(
+            "INSERT INTO paused_jobs (job, paused_at) VALUES (?, ?) ON CONFLICT (job) DO NOTHING",
+        )
+        .bind(job)
+        .bind(truncate_to_millis(now))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn resume(&self, job: &str) -> DbResult<bool> {
+        let result = query("DELETE FROM paused_jobs WHERE job = ?")
+            .bind(job)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list_paused(&self) -> DbResult<Vec<String>> {
+        let rows = query("SELECT job FROM paused_jobs ORDER BY job ASC")
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows.iter().map(|row| row.col("job")).collect())
+    }
+}
@@ -1,24 +1,45 @@
+mod admin_passkeys;
+mod admin_totp;
+mod agents;
 mod api_keys;
 mod audit_logs;
 pub(crate) mod backend;
+mod client_cert_mappings;
 mod common;
 mod containers;
 mod conversations;
 #[cfg(feature = "sso")]
 mod domain_verifications;
+mod email_log;
+mod federation;
 mod files;
+mod fine_tuning_jobs;
+mod http_tools;
+mod idempotency_keys;
+mod invitations;
+mod job_leaders;
+mod job_runs;
 #[cfg(feature = "mcp")]
 mod mcp_pending_approvals;
+mod model_catalog_entries;
 mod model_pricing;
 mod oauth_authorization_codes;
 mod org_rbac_policies;
+mod org_request_policies;
 #[cfg(feature = "sso")]
 mod org_sso_configs;
 mod organizations;
 mod projects;
+mod provider_files;
+mod provider_probes;
+mod provider_settings;
+mod provider_usage_imports;
 mod providers;
 mod response_events;
 mod responses;
+mod role_elevations;
+mod scheduled_reports;
+mod scheduled_tasks;
 #[cfg(feature = "sso")]
 mod scim_configs;
 #[cfg(feature = "sso")]
@@ -26,34 +47,58 @@ mod scim_group_mappings;
 #[cfg(feature = "sso")]
 mod scim_user_mappings;
 mod service_accounts;
+mod shadow_results;
 mod skills;
 #[cfg(feature = "sso")]
 mod sso_group_mappings;
 mod teams;
 mod templates;
 mod usage;
+mod usage_anomalies;
 mod users;
+mod vector_store_syncs;
 mod vector_stores;
 
+pub use admin_passkeys::SqliteAdminPasskeyRepo;
+pub use admin_totp::SqliteAdminTotpRepo;
+pub use agents::SqliteAgentRepo;
 pub use api_keys::SqliteApiKeyRepo;
 pub use audit_logs::SqliteAuditLogRepo;
+pub use client_cert_mappings::SqliteClientCertMappingRepo;
 pub use containers::SqliteContainersRepo;
 pub use conversations::SqliteConversationRepo;
 #[cfg(feature = "sso")]
 pub use domain_verifications::SqliteDomainVerificationRepo;
+pub use email_log::SqliteEmailLogRepo;
+pub use federation::SqliteFederationRepo;
 pub use files::SqliteFilesRepo;
+pub use fine_tuning_jobs::SqliteFineTuningJobRepo;
+pub use http_tools::SqliteHttpToolRepo;
+pub use idempotency_keys::SqliteIdempotencyKeyRepo;
+pub use invitations::SqliteInvitationRepo;
+pub use job_leaders::SqliteJobLeaderRepo;
+pub use job_runs::SqliteJobRunRepo;
 #[cfg(feature = "mcp")]
 pub use mcp_pending_approvals::SqliteMcpPendingApprovalsRepo;
+pub use model_catalog_entries::SqliteModelCatalogEntryRepo;
 pub use model_pricing::SqliteModelPricingRepo;
 pub use oauth_authorization_codes::SqliteOAuthAuthorizationCodeRepo;
 pub use org_rbac_policies::SqliteOrgRbacPolicyRepo;
+pub use org_request_policies::SqliteOrgRequestPolicyRepo;
 #[cfg(feature = "sso")]
 pub use org_sso_configs::SqliteOrgSsoConfigRepo;
 pub use organizations::SqliteOrganizationRepo;
 pub use projects::SqliteProjectRepo;
+pub use provider_files::SqliteProviderFileRepo;
+pub use provider_probes::SqliteProviderProbeRepo;
+pub use provider_settings::SqliteProviderSettingsRepo;
+pub use provider_usage_imports::SqliteProviderUsageImportRepo;
 pub use providers::SqliteDynamicProviderRepo;
 pub use response_events::SqliteResponseEventsRepo;
 pub use responses::SqliteResponsesRepo;
+pub use role_elevations::SqliteRoleElevationRepo;
+pub use scheduled_reports::SqliteReportRunRepo;
+pub use scheduled_tasks::SqliteScheduledTaskRepo;
 #[cfg(feature = "sso")]
 pub use scim_configs::SqliteOrgScimConfigRepo;
 #[cfg(feature = "sso")]
@@ -61,11 +106,14 @@ pub use scim_group_mappings::SqliteScimGroupMappingRepo;
 #[cfg(feature = "sso")]
 pub use scim_user_mappings::SqliteScimUserMappingRepo;
 pub use service_accounts::SqliteServiceAccountRepo;
+pub use shadow_results::SqliteShadowResultRepo;
 pub use skills::SqliteSkillRepo;
 #[cfg(feature = "sso")]
 pub use sso_group_mappings::SqliteSsoGroupMappingRepo;
 pub use teams::SqliteTeamRepo;
 pub use templates::SqliteTemplateRepo;
 pub use usage::SqliteUsageRepo;
+pub use usage_anomalies::SqliteUsageAnomalyRepo;
 pub use users::SqliteUserRepo;
+pub use vector_store_syncs::SqliteVectorStoreSyncRepo;
 pub use vector_stores::SqliteVectorStoresRepo;
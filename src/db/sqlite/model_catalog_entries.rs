@@ -0,0 +1,130 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use super::{
+    backend::{Pool, Row, RowExt, map_unique_violation, query},
+    common::parse_uuid,
+};
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{ModelCatalogEntryRepo, truncate_to_millis},
+    },
+    models::{CatalogEntryMetadata, CreateModelCatalogEntry, ModelCatalogEntry},
+};
+
+const COLUMNS: &str = "id, provider, model, metadata, created_at, updated_at";
+
+pub struct SqliteModelCatalogEntryRepo {
+    pool: Pool,
+}
+
+impl SqliteModelCatalogEntryRepo {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    fn serialize_metadata(metadata: &CatalogEntryMetadata) -> DbResult<String> {
+        serde_json::to_string(metadata)
+            .map_err(|e| DbError::Internal(format!("failed to serialize metadata: {e}")))
+    }
+
+    fn parse_entry(row: &Row) -> DbResult<ModelCatalogEntry> {
+        let metadata = serde_json::from_str(&row.col::<String>("metadata"))
+            .map_err(|e| DbError::Internal(format!("failed to deserialize metadata: {e}")))?;
+        Ok(ModelCatalogEntry {
+            id: parse_uuid(&row.col::<String>("id"))?,
+            provider: row.col("provider"),
+            model: row.col("model"),
+            metadata,
+            created_at: row.col("created_at"),
+            updated_at: row.col("updated_at"),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl ModelCatalogEntryRepo for SqliteModelCatalogEntryRepo {
+    async fn create(&self, input: CreateModelCatalogEntry) -> DbResult<ModelCatalogEntry> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+
+        query(
+            r#"
+            INSERT INTO model_catalog_entries (id, provider, model, metadata, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(id.to_string())
+        .bind(&input.provider)
+        .bind(&input.model)
+        .bind(Self::serialize_metadata(&input.metadata)?)
+        .bind(now)
+        .bind(now)
+        .execute(&self.pool)
+        .await
+        .map_err(map_unique_violation(format!(
+            "Catalog entry for '{}/{}' already exists",
+            input.provider, input.model
+        )))?;
+
+        Ok(ModelCatalogEntry {
+            id,
+            provider: input.provider,
+            model: input.model,
+            metadata: input.metadata,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<ModelCatalogEntry>> {
+        let sql = format!("SELECT {COLUMNS} FROM model_catalog_entries WHERE id = ?");
+        let row = query(&sql)
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|r| Self::parse_entry(&r)).transpose()
+    }
+
+    async fn list(&self) -> DbResult<Vec<ModelCatalogEntry>> {
+        let sql =
+            format!("SELECT {COLUMNS} FROM model_catalog_entries ORDER BY provider ASC, model ASC");
+        let rows = query(&sql).fetch_all(&self.pool).await?;
+
+        rows.iter().map(Self::parse_entry).collect()
+    }
+
+    async fn update(
+        &self,
+        id: Uuid,
+        metadata: CatalogEntryMetadata,
+    ) -> DbResult<ModelCatalogEntry> {
+        let now = truncate_to_millis(Utc::now());
+        let sql = format!(
+            "UPDATE model_catalog_entries SET metadata = ?, updated_at = ? WHERE id = ? \
+             RETURNING {COLUMNS}"
+        );
+        let row = query(&sql)
+            .bind(Self::serialize_metadata(&metadata)?)
+            .bind(now)
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or(DbError::NotFound)?;
+
+        Self::parse_entry(&row)
+    }
+
+    async fn delete(&self, id: Uuid) -> DbResult<bool> {
+        let result = query("DELETE FROM model_catalog_entries WHERE id = ?")
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,226 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use super::{
+    backend::{Pool, Row, RowExt, map_unique_violation, query},
+    common::parse_uuid,
+};
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{OrgRequestPolicyRepo, truncate_to_millis},
+    },
+    models::{CreateOrgRequestPolicy, OrgRequestPolicy, UpdateOrgRequestPolicy},
+};
+
+const COLUMNS: &str = "id, org_id, name, description, condition, action, modifications, message, \
+                       priority, enabled, created_at, updated_at";
+
+pub struct SqliteOrgRequestPolicyRepo {
+    pool: Pool,
+}
+
+impl SqliteOrgRequestPolicyRepo {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    fn parse_policy(row: &Row) -> DbResult<OrgRequestPolicy> {
+        let modifications = row
+            .col::<Option<String>>("modifications")
+            .map(|json| serde_json::from_str(&json))
+            .transpose()
+            .map_err(|e| DbError::Internal(format!("failed to deserialize modifications: {e}")))?;
+        let enabled: i32 = row.col("enabled");
+
+        Ok(OrgRequestPolicy {
+            id: parse_uuid(&row.col::<String>("id"))?,
+            org_id: parse_uuid(&row.col::<String>("org_id"))?,
+            name: row.col("name"),
+            description: row.col("description"),
+            condition: row.col("condition"),
+            action: row
+                .col::<String>("action")
+                .parse()
+                .map_err(DbError::Internal)?,
+            modifications,
+            message: row.col("message"),
+            priority: row.col("priority"),
+            enabled: enabled != 0,
+            created_at: row.col("created_at"),
+            updated_at: row.col("updated_at"),
+        })
+    }
+
+    async fn list(&self, org_id: Uuid, enabled_only: bool) -> DbResult<Vec<OrgRequestPolicy>> {
+        let filter = if enabled_only { "AND enabled = 1" } else { "" };
+        let sql = format!(
+            "SELECT {COLUMNS} FROM org_request_policies WHERE org_id = ? {filter} \
+             ORDER BY priority DESC, name ASC"
+        );
+        let rows = query(&sql)
+            .bind(org_id.to_string())
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter().map(Self::parse_policy).collect()
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl OrgRequestPolicyRepo for SqliteOrgRequestPolicyRepo {
+    async fn create(
+        &self,
+        org_id: Uuid,
+        input: CreateOrgRequestPolicy,
+    ) -> DbResult<OrgRequestPolicy> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+
+        query(
+            r#"
+            INSERT INTO org_request_policies (
+                id, org_id, name, description, condition, action, modifications, message,
+                priority, enabled, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(id.to_string())
+        .bind(org_id.to_string())
+        .bind(&input.name)
+        .bind(&input.description)
+        .bind(&input.condition)
+        .bind(input.action.as_str())
+        .bind(input.modifications.as_ref().map(|m| m.to_string()))
+        .bind(&input.message)
+        .bind(input.priority)
+        .bind(if input.enabled { 1 } else { 0 })
+        .bind(now)
+        .bind(now)
+        .execute(&self.pool)
+        .await
+        .map_err(map_unique_violation(format!(
+            "A request policy named '{}' already exists in this organization",
+            input.name
+        )))?;
+
+        Ok(OrgRequestPolicy {
+            id,
+            org_id,
+            name: input.name,
+            description: input.description,
+            condition: input.condition,
+            action: input.action,
+            modifications: input.modifications,
+            message: input.message,
+            priority: input.priority,
+            enabled: input.enabled,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<OrgRequestPolicy>> {
+        let sql = format!("SELECT {COLUMNS} FROM org_request_policies WHERE id = ?");
+        let row = query(&sql)
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|r| Self::parse_policy(&r)).transpose()
+    }
+
+    async fn list_by_org(&self, org_id: Uuid) -> DbResult<Vec<OrgRequestPolicy>> {
+        self.list(org_id, false).await
+    }
+
+    async fn list_enabled_by_org(&self, org_id: Uuid) -> DbResult<Vec<OrgRequestPolicy>> {
+        self.list(org_id, true).await
+    }
+
+    async fn update(&self, id: Uuid, input: UpdateOrgRequestPolicy) -> DbResult<OrgRequestPolicy> {
+        let mut policy = self.get_by_id(id).await?.ok_or(DbError::NotFound)?;
+
+        if let Some(name) = input.name {
+            policy.name = name;
+        }
+        if let Some(description) = input.description {
+            policy.description = description;
+        }
+        if let Some(condition) = input.condition {
+            policy.condition = condition;
+        }
+        if let Some(action) = input.action {
+            policy.action = action;
+        }
+        if let Some(modifications) = input.modifications {
+            policy.modifications = modifications;
+        }
+        if let Some(message) = input.message {
+            policy.message = message;
+        }
+        if let Some(priority) = input.priority {
+            policy.priority = priority;
+        }
+        if let Some(enabled) = input.enabled {
+            policy.enabled = enabled;
+        }
+        policy.updated_at = truncate_to_millis(Utc::now());
+
+        let result = query(
+            r#"
+            UPDATE org_request_policies
+            SET name = ?, description = ?, condition = ?, action = ?, modifications = ?,
+                message = ?, priority = ?, enabled = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(&policy.name)
+        .bind(&policy.description)
+        .bind(&policy.condition)
+        .bind(policy.action.as_str())
+        .bind(policy.modifications.as_ref().map(|m| m.to_string()))
+        .bind(&policy.message)
+        .bind(policy.priority)
+        .bind(if policy.enabled { 1 } else { 0 })
+        .bind(policy.updated_at)
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await
+        .map_err(map_unique_violation(format!(
+            "A request policy named '{}' already exists in this organization",
+            policy.name
+        )))?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        Ok(policy)
+    }
+
+    async fn delete(&self, id: Uuid) -> DbResult<()> {
+        let result = query("DELETE FROM org_request_policies WHERE id = ?")
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        Ok(())
+    }
+
+    async fn count_by_org(&self, org_id: Uuid) -> DbResult<i64> {
+        let row = query("SELECT COUNT(*) as count FROM org_request_policies WHERE org_id = ?")
+            .bind(org_id.to_string())
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(row.col::<i64>("count"))
+    }
+}
@@ -12,7 +12,7 @@ use crate::{
     },
     models::{
         CreateOrgSsoConfig, OrgSsoConfig, OrgSsoConfigWithSecret, SsoEnforcementMode,
-        SsoProviderType, UpdateOrgSsoConfig,
+        SsoProviderType, SsoRoleRule, UpdateOrgSsoConfig,
     },
 };
 
@@ -498,6 +498,45 @@ impl OrgSsoConfigRepo for SqliteOrgSsoConfigRepo {
         Ok(())
     }
 
+    async fn get_role_rules(&self, org_id: Uuid) -> DbResult<Vec<SsoRoleRule>> {
+        let row = query("SELECT role_rules FROM org_sso_configs WHERE org_id = ?")
+            .bind(org_id.to_string())
+            .fetch_optional(&self.pool)
+            .await?;
+
+        let Some(json) = row.and_then(|r| r.col::<Option<String>>("role_rules")) else {
+            return Ok(Vec::new());
+        };
+        serde_json::from_str(&json)
+            .map_err(|e| DbError::Internal(format!("Invalid role_rules JSON: {e}")))
+    }
+
+    async fn set_role_rules(&self, org_id: Uuid, rules: &[SsoRoleRule]) -> DbResult<()> {
+        let json =
+            if rules.is_empty() {
+                None
+            } else {
+                Some(serde_json::to_string(rules).map_err(|e| {
+                    DbError::Internal(format!("Failed to serialize role rules: {e}"))
+                })?)
+            };
+        let now = truncate_to_millis(chrono::Utc::now());
+
+        let result =
+            query("UPDATE org_sso_configs SET role_rules = ?, updated_at = ? WHERE org_id = ?")
+                .bind(json)
+                .bind(now)
+                .bind(org_id.to_string())
+                .execute(&self.pool)
+                .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        Ok(())
+    }
+
     async fn find_enabled_oidc_by_issuer(&self, issuer: &str) -> DbResult<Vec<OrgSsoConfig>> {
         let rows = query(
             r#"
@@ -682,6 +721,7 @@ mod tests {
                 allowed_email_domains TEXT,
                 sync_attributes_on_login INTEGER NOT NULL DEFAULT 0,
                 sync_memberships_on_login INTEGER NOT NULL DEFAULT 1,
+                role_rules TEXT,
                 enforcement_mode TEXT NOT NULL DEFAULT 'optional',
                 enabled INTEGER NOT NULL DEFAULT 1,
                 created_at TEXT NOT NULL DEFAULT (datetime('now')),
@@ -898,6 +938,47 @@ mod tests {
         assert_eq!(with_secret.client_secret_key, Some("new-key".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_role_rules_roundtrip() {
+        let pool = create_test_pool().await;
+        let org_id = create_test_org(&pool, "test-org").await;
+        let team_id = create_test_team(&pool, org_id, "ml").await;
+        let repo = SqliteOrgSsoConfigRepo::new(pool);
+
+        // No SSO config yet
+        assert!(repo.get_role_rules(org_id).await.unwrap().is_empty());
+        assert!(matches!(
+            repo.set_role_rules(org_id, &[]).await,
+            Err(DbError::NotFound)
+        ));
+
+        repo.create(org_id, make_test_input(), Some("key"), None)
+            .await
+            .expect("Failed to create");
+
+        let rules = vec![SsoRoleRule {
+            name: "ml-engineers".to_string(),
+            conditions: vec![crate::models::ClaimCondition {
+                claim: "department".to_string(),
+                operator: crate::models::ClaimOperator::Equals,
+                value: Some(serde_json::json!("ML")),
+            }],
+            team_id: Some(team_id),
+            project_id: None,
+            role: "engineer".to_string(),
+            priority: 0,
+        }];
+        repo.set_role_rules(org_id, &rules)
+            .await
+            .expect("Failed to set rules");
+        assert_eq!(repo.get_role_rules(org_id).await.unwrap(), rules);
+
+        repo.set_role_rules(org_id, &[])
+            .await
+            .expect("Failed to clear rules");
+        assert!(repo.get_role_rules(org_id).await.unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn test_delete_config() {
         let pool = create_test_pool().await;
@@ -1,8 +1,10 @@
+use std::collections::BTreeMap;
+
 use async_trait::async_trait;
 use uuid::Uuid;
 
 use super::{
-    backend::{Pool, RowExt, map_unique_violation, query},
+    backend::{Pool, Row, RowExt, map_unique_violation, query},
     common::parse_uuid,
 };
 use crate::{
@@ -13,9 +15,72 @@ use crate::{
             cursor_from_row, truncate_to_millis,
         },
     },
-    models::{CreateOrganization, Organization, UpdateOrganization},
+    models::{
+        CreateOrganization, ModelAccessPolicy, ModelDegradationPolicy, OrgEntitlements,
+        OrgNetworkPolicy, Organization, ParameterGovernance, UpdateOrganization,
+    },
 };
 
+fn org_from_row(row: &Row) -> DbResult<Organization> {
+    Ok(Organization {
+        id: parse_uuid(&row.col::<String>("id"))?,
+        slug: row.col("slug"),
+        name: row.col("name"),
+        data_residency: row
+            .col::<Option<String>>("data_residency")
+            .map(|s| serde_json::from_str(&s))
+            .transpose()
+            .map_err(|e| DbError::Internal(format!("failed to deserialize data_residency: {e}")))?,
+        request_defaults: row
+            .col::<Option<String>>("request_defaults")
+            .map(|s| serde_json::from_str(&s))
+            .transpose()
+            .map_err(|e| {
+                DbError::Internal(format!("failed to deserialize request_defaults: {e}"))
+            })?,
+        conversation_summaries: row
+            .col::<Option<String>>("conversation_summaries")
+            .map(|s| serde_json::from_str(&s))
+            .transpose()
+            .map_err(|e| {
+                DbError::Internal(format!("failed to deserialize conversation_summaries: {e}"))
+            })?,
+        network_policy: row
+            .col::<Option<String>>("network_policy")
+            .map(|s| serde_json::from_str(&s))
+            .transpose()
+            .map_err(|e| DbError::Internal(format!("failed to deserialize network_policy: {e}")))?,
+        parameter_governance: row
+            .col::<Option<String>>("parameter_governance")
+            .map(|s| serde_json::from_str(&s))
+            .transpose()
+            .map_err(|e| {
+                DbError::Internal(format!("failed to deserialize parameter_governance: {e}"))
+            })?,
+        model_access: row
+            .col::<Option<String>>("model_access")
+            .map(|s| serde_json::from_str(&s))
+            .transpose()
+            .map_err(|e| DbError::Internal(format!("failed to deserialize model_access: {e}")))?,
+        model_degradation: row
+            .col::<Option<String>>("model_degradation")
+            .map(|s| serde_json::from_str(&s))
+            .transpose()
+            .map_err(|e| {
+                DbError::Internal(format!("failed to deserialize model_degradation: {e}"))
+            })?,
+        entitlements: row
+            .col::<Option<String>>("entitlements")
+            .map(|s| serde_json::from_str(&s))
+            .transpose()
+            .map_err(|e| DbError::Internal(format!("failed to deserialize entitlements: {e}")))?,
+        labels: serde_json::from_str(&row.col::<String>("labels"))
+            .map_err(|e| DbError::Internal(format!("failed to deserialize labels: {e}")))?,
+        created_at: row.col("created_at"),
+        updated_at: row.col("updated_at"),
+    })
+}
+
 pub struct SqliteOrganizationRepo {
     pool: Pool,
 }
@@ -46,10 +111,11 @@ impl SqliteOrganizationRepo {
 
         let sql = format!(
             r#"
-            SELECT id, slug, name, created_at, updated_at
+            SELECT id, slug, name, data_residency, request_defaults, conversation_summaries, network_policy, parameter_governance, model_access, model_degradation, entitlements, labels, created_at, updated_at
             FROM organizations
             WHERE (created_at, id) {} (?, ?)
             {}
+            AND NOT EXISTS (SELECT 1 FROM json_each(?) l WHERE json_extract(labels, '$."' || l.key || '"') IS NOT l.value)
             ORDER BY created_at {}, id {}
             LIMIT ?
             "#,
@@ -59,6 +125,7 @@ impl SqliteOrganizationRepo {
         let rows = query(&sql)
             .bind(cursor.created_at)
             .bind(cursor.id.to_string())
+            .bind(params.labels.to_json().to_string())
             .bind(fetch_limit)
             .fetch_all(&self.pool)
             .await?;
@@ -67,15 +134,7 @@ impl SqliteOrganizationRepo {
         let mut items: Vec<Organization> = rows
             .into_iter()
             .take(limit as usize)
-            .map(|row| {
-                Ok(Organization {
-                    id: parse_uuid(&row.col::<String>("id"))?,
-                    slug: row.col("slug"),
-                    name: row.col("name"),
-                    created_at: row.col("created_at"),
-                    updated_at: row.col("updated_at"),
-                })
-            })
+            .map(|row| org_from_row(&row))
             .collect::<DbResult<Vec<_>>>()?;
 
         if should_reverse {
@@ -121,6 +180,15 @@ impl OrganizationRepo for SqliteOrganizationRepo {
             id,
             slug: input.slug,
             name: input.name,
+            data_residency: None,
+            request_defaults: None,
+            conversation_summaries: None,
+            network_policy: None,
+            parameter_governance: None,
+            model_access: None,
+            model_degradation: None,
+            entitlements: None,
+            labels: Default::default(),
             created_at: now,
             updated_at: now,
         })
@@ -129,7 +197,7 @@ impl OrganizationRepo for SqliteOrganizationRepo {
     async fn get_by_id(&self, id: Uuid) -> DbResult<Option<Organization>> {
         let result = query(
             r#"
-            SELECT id, slug, name, created_at, updated_at
+            SELECT id, slug, name, data_residency, request_defaults, conversation_summaries, network_policy, parameter_governance, model_access, model_degradation, entitlements, labels, created_at, updated_at
             FROM organizations
             WHERE id = ? AND deleted_at IS NULL
             "#,
@@ -139,13 +207,7 @@ impl OrganizationRepo for SqliteOrganizationRepo {
         .await?;
 
         match result {
-            Some(row) => Ok(Some(Organization {
-                id: parse_uuid(&row.col::<String>("id"))?,
-                slug: row.col("slug"),
-                name: row.col("name"),
-                created_at: row.col("created_at"),
-                updated_at: row.col("updated_at"),
-            })),
+            Some(row) => org_from_row(&row).map(Some),
             None => Ok(None),
         }
     }
@@ -153,7 +215,7 @@ impl OrganizationRepo for SqliteOrganizationRepo {
     async fn get_by_slug(&self, slug: &str) -> DbResult<Option<Organization>> {
         let result = query(
             r#"
-            SELECT id, slug, name, created_at, updated_at
+            SELECT id, slug, name, data_residency, request_defaults, conversation_summaries, network_policy, parameter_governance, model_access, model_degradation, entitlements, labels, created_at, updated_at
             FROM organizations
             WHERE slug = ? AND deleted_at IS NULL
             "#,
@@ -163,13 +225,7 @@ impl OrganizationRepo for SqliteOrganizationRepo {
         .await?;
 
         match result {
-            Some(row) => Ok(Some(Organization {
-                id: parse_uuid(&row.col::<String>("id"))?,
-                slug: row.col("slug"),
-                name: row.col("name"),
-                created_at: row.col("created_at"),
-                updated_at: row.col("updated_at"),
-            })),
+            Some(row) => org_from_row(&row).map(Some),
             None => Ok(None),
         }
     }
@@ -189,36 +245,34 @@ impl OrganizationRepo for SqliteOrganizationRepo {
         // First page (no cursor provided)
         let sql = if params.include_deleted {
             r#"
-            SELECT id, slug, name, created_at, updated_at
+            SELECT id, slug, name, data_residency, request_defaults, conversation_summaries, network_policy, parameter_governance, model_access, model_degradation, entitlements, labels, created_at, updated_at
             FROM organizations
+            WHERE NOT EXISTS (SELECT 1 FROM json_each(?) l WHERE json_extract(labels, '$."' || l.key || '"') IS NOT l.value)
             ORDER BY created_at DESC, id DESC
             LIMIT ?
             "#
         } else {
             r#"
-            SELECT id, slug, name, created_at, updated_at
+            SELECT id, slug, name, data_residency, request_defaults, conversation_summaries, network_policy, parameter_governance, model_access, model_degradation, entitlements, labels, created_at, updated_at
             FROM organizations
             WHERE deleted_at IS NULL
+            AND NOT EXISTS (SELECT 1 FROM json_each(?) l WHERE json_extract(labels, '$."' || l.key || '"') IS NOT l.value)
             ORDER BY created_at DESC, id DESC
             LIMIT ?
             "#
         };
 
-        let rows = query(sql).bind(fetch_limit).fetch_all(&self.pool).await?;
+        let rows = query(sql)
+            .bind(params.labels.to_json().to_string())
+            .bind(fetch_limit)
+            .fetch_all(&self.pool)
+            .await?;
 
         let has_more = rows.len() as i64 > limit;
         let items: Vec<Organization> = rows
             .into_iter()
             .take(limit as usize)
-            .map(|row| {
-                Ok(Organization {
-                    id: parse_uuid(&row.col::<String>("id"))?,
-                    slug: row.col("slug"),
-                    name: row.col("name"),
-                    created_at: row.col("created_at"),
-                    updated_at: row.col("updated_at"),
-                })
-            })
+            .map(|row| org_from_row(&row))
             .collect::<DbResult<Vec<_>>>()?;
 
         // Generate cursors for pagination
@@ -242,31 +296,260 @@ impl OrganizationRepo for SqliteOrganizationRepo {
     }
 
     async fn update(&self, id: Uuid, input: UpdateOrganization) -> DbResult<Organization> {
-        if let Some(name) = input.name {
-            let now = truncate_to_millis(chrono::Utc::now());
-
-            let result = query(
-                r#"
-                UPDATE organizations
-                SET name = ?, updated_at = ?
-                WHERE id = ? AND deleted_at IS NULL
-                "#,
-            )
-            .bind(&name)
-            .bind(now)
+        let has_name_update = input.name.is_some();
+        let has_residency_update = input.data_residency.is_some();
+        let has_defaults_update = input.request_defaults.is_some();
+        let has_summaries_update = input.conversation_summaries.is_some();
+
+        if !has_name_update
+            && !has_residency_update
+            && !has_defaults_update
+            && !has_summaries_update
+        {
+            return self.get_by_id(id).await?.ok_or(DbError::NotFound);
+        }
+
+        let now = truncate_to_millis(chrono::Utc::now());
+
+        // Build dynamic update query
+        let mut set_clauses = vec!["updated_at = ?"];
+        if has_name_update {
+            set_clauses.push("name = ?");
+        }
+        if has_residency_update {
+            set_clauses.push("data_residency = ?");
+        }
+        if has_defaults_update {
+            set_clauses.push("request_defaults = ?");
+        }
+        if has_summaries_update {
+            set_clauses.push("conversation_summaries = ?");
+        }
+
+        let sql = format!(
+            "UPDATE organizations SET {} WHERE id = ? AND deleted_at IS NULL",
+            set_clauses.join(", ")
+        );
+
+        let mut query_builder = query(&sql).bind(now);
+
+        if let Some(ref name) = input.name {
+            query_builder = query_builder.bind(name);
+        }
+        if let Some(ref policy) = input.data_residency {
+            query_builder =
+                query_builder.bind(policy.as_ref().and_then(|p| serde_json::to_string(p).ok()));
+        }
+        if let Some(ref defaults) = input.request_defaults {
+            query_builder = query_builder.bind(
+                defaults
+                    .as_ref()
+                    .and_then(|d| serde_json::to_string(d).ok()),
+            );
+        }
+        if let Some(ref settings) = input.conversation_summaries {
+            query_builder = query_builder.bind(
+                settings
+                    .as_ref()
+                    .and_then(|s| serde_json::to_string(s).ok()),
+            );
+        }
+
+        let result = query_builder
             .bind(id.to_string())
             .execute(&self.pool)
             .await?;
 
-            if result.rows_affected() == 0 {
-                return Err(DbError::NotFound);
-            }
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
 
-            // Return the updated org
-            self.get_by_id(id).await?.ok_or(DbError::NotFound)
-        } else {
-            self.get_by_id(id).await?.ok_or(DbError::NotFound)
+        self.get_by_id(id).await?.ok_or(DbError::NotFound)
+    }
+
+    async fn set_network_policy(
+        &self,
+        id: Uuid,
+        policy: Option<&OrgNetworkPolicy>,
+    ) -> DbResult<Organization> {
+        let now = truncate_to_millis(chrono::Utc::now());
+        let policy = policy
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| DbError::Internal(format!("failed to serialize network_policy: {e}")))?;
+
+        let result = query(
+            r#"
+            UPDATE organizations
+            SET network_policy = ?, updated_at = ?
+            WHERE id = ? AND deleted_at IS NULL
+            "#,
+        )
+        .bind(policy)
+        .bind(now)
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        self.get_by_id(id).await?.ok_or(DbError::NotFound)
+    }
+
+    async fn set_parameter_governance(
+        &self,
+        id: Uuid,
+        governance: Option<&ParameterGovernance>,
+    ) -> DbResult<Organization> {
+        let now = truncate_to_millis(chrono::Utc::now());
+        let governance = governance
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| {
+                DbError::Internal(format!("failed to serialize parameter_governance: {e}"))
+            })?;
+
+        let result = query(
+            r#"
+            UPDATE organizations
+            SET parameter_governance = ?, updated_at = ?
+            WHERE id = ? AND deleted_at IS NULL
+            "#,
+        )
+        .bind(governance)
+        .bind(now)
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        self.get_by_id(id).await?.ok_or(DbError::NotFound)
+    }
+
+    async fn set_model_access(
+        &self,
+        id: Uuid,
+        policy: Option<&ModelAccessPolicy>,
+    ) -> DbResult<Organization> {
+        let now = truncate_to_millis(chrono::Utc::now());
+        let policy = policy
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| DbError::Internal(format!("failed to serialize model_access: {e}")))?;
+
+        let result = query(
+            r#"
+            UPDATE organizations
+            SET model_access = ?, updated_at = ?
+            WHERE id = ? AND deleted_at IS NULL
+            "#,
+        )
+        .bind(policy)
+        .bind(now)
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        self.get_by_id(id).await?.ok_or(DbError::NotFound)
+    }
+
+    async fn set_model_degradation(
+        &self,
+        id: Uuid,
+        policy: Option<&ModelDegradationPolicy>,
+    ) -> DbResult<Organization> {
+        let now = truncate_to_millis(chrono::Utc::now());
+        let policy = policy.map(serde_json::to_string).transpose().map_err(|e| {
+            DbError::Internal(format!("failed to serialize model_degradation: {e}"))
+        })?;
+
+        let result = query(
+            r#"
+            UPDATE organizations
+            SET model_degradation = ?, updated_at = ?
+            WHERE id = ? AND deleted_at IS NULL
+            "#,
+        )
+        .bind(policy)
+        .bind(now)
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        self.get_by_id(id).await?.ok_or(DbError::NotFound)
+    }
+
+    async fn set_entitlements(
+        &self,
+        id: Uuid,
+        entitlements: Option<&OrgEntitlements>,
+    ) -> DbResult<Organization> {
+        let now = truncate_to_millis(chrono::Utc::now());
+        let entitlements = entitlements
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| DbError::Internal(format!("failed to serialize entitlements: {e}")))?;
+
+        let result = query(
+            r#"
+            UPDATE organizations
+            SET entitlements = ?, updated_at = ?
+            WHERE id = ? AND deleted_at IS NULL
+            "#,
+        )
+        .bind(entitlements)
+        .bind(now)
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
         }
+
+        self.get_by_id(id).await?.ok_or(DbError::NotFound)
+    }
+
+    async fn set_labels(
+        &self,
+        id: Uuid,
+        labels: &BTreeMap<String, String>,
+    ) -> DbResult<Organization> {
+        let now = truncate_to_millis(chrono::Utc::now());
+        let labels = serde_json::to_string(labels)
+            .map_err(|e| DbError::Internal(format!("failed to serialize labels: {e}")))?;
+
+        let result = query(
+            r#"
+            UPDATE organizations
+            SET labels = ?, updated_at = ?
+            WHERE id = ? AND deleted_at IS NULL
+            "#,
+        )
+        .bind(labels)
+        .bind(now)
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        self.get_by_id(id).await?.ok_or(DbError::NotFound)
     }
 
     async fn delete(&self, id: Uuid) -> DbResult<()> {
@@ -314,6 +597,15 @@ mod tests {
                 id TEXT PRIMARY KEY NOT NULL,
                 slug TEXT NOT NULL UNIQUE,
                 name TEXT NOT NULL,
+                data_residency TEXT,
+                request_defaults TEXT,
+                conversation_summaries TEXT,
+                network_policy TEXT,
+                parameter_governance TEXT,
+                model_access TEXT,
+                model_degradation TEXT,
+                entitlements TEXT,
+                labels TEXT NOT NULL DEFAULT '{}',
                 created_at TEXT NOT NULL DEFAULT (datetime('now')),
                 updated_at TEXT NOT NULL DEFAULT (datetime('now')),
                 deleted_at TEXT
@@ -549,6 +841,9 @@ mod tests {
                 created.id,
                 UpdateOrganization {
                     name: Some("Updated Name".to_string()),
+                    data_residency: None,
+                    request_defaults: None,
+                    conversation_summaries: None,
                 },
             )
             .await
@@ -571,7 +866,15 @@ mod tests {
             .expect("Failed to create org");
 
         let result = repo
-            .update(created.id, UpdateOrganization { name: None })
+            .update(
+                created.id,
+                UpdateOrganization {
+                    name: None,
+                    data_residency: None,
+                    request_defaults: None,
+                    conversation_summaries: None,
+                },
+            )
             .await
             .expect("Failed to update org");
 
@@ -588,6 +891,9 @@ mod tests {
                 Uuid::new_v4(),
                 UpdateOrganization {
                     name: Some("New Name".to_string()),
+                    data_residency: None,
+                    request_defaults: None,
+                    conversation_summaries: None,
                 },
             )
             .await;
@@ -739,6 +1045,9 @@ mod tests {
                 created.id,
                 UpdateOrganization {
                     name: Some("New Name".to_string()),
+                    data_residency: None,
+                    request_defaults: None,
+                    conversation_summaries: None,
                 },
             )
             .await;
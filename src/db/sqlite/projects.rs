@@ -1,8 +1,10 @@
+use std::collections::BTreeMap;
+
 use async_trait::async_trait;
 use uuid::Uuid;
 
 use super::{
-    backend::{Pool, RowExt, map_unique_violation, query},
+    backend::{Pool, Row, RowExt, map_unique_violation, query},
     common::parse_uuid,
 };
 use crate::{
@@ -13,9 +15,43 @@ use crate::{
             cursor_from_row, truncate_to_millis,
         },
     },
-    models::{CreateProject, Project, UpdateProject},
+    models::{CreateProject, ModelAccessPolicy, ParameterGovernance, Project, UpdateProject},
 };
 
+fn project_from_row(row: &Row) -> DbResult<Project> {
+    let team_id: Option<String> = row.col("team_id");
+    Ok(Project {
+        id: parse_uuid(&row.col::<String>("id"))?,
+        org_id: parse_uuid(&row.col::<String>("org_id"))?,
+        team_id: team_id.as_deref().map(parse_uuid).transpose()?,
+        slug: row.col("slug"),
+        name: row.col("name"),
+        request_defaults: row
+            .col::<Option<String>>("request_defaults")
+            .map(|s| serde_json::from_str(&s))
+            .transpose()
+            .map_err(|e| {
+                DbError::Internal(format!("failed to deserialize request_defaults: {e}"))
+            })?,
+        parameter_governance: row
+            .col::<Option<String>>("parameter_governance")
+            .map(|s| serde_json::from_str(&s))
+            .transpose()
+            .map_err(|e| {
+                DbError::Internal(format!("failed to deserialize parameter_governance: {e}"))
+            })?,
+        model_access: row
+            .col::<Option<String>>("model_access")
+            .map(|s| serde_json::from_str(&s))
+            .transpose()
+            .map_err(|e| DbError::Internal(format!("failed to deserialize model_access: {e}")))?,
+        labels: serde_json::from_str(&row.col::<String>("labels"))
+            .map_err(|e| DbError::Internal(format!("failed to deserialize labels: {e}")))?,
+        created_at: row.col("created_at"),
+        updated_at: row.col("updated_at"),
+    })
+}
+
 pub struct SqliteProjectRepo {
     pool: Pool,
 }
@@ -47,10 +83,11 @@ impl SqliteProjectRepo {
 
         let sql = format!(
             r#"
-            SELECT id, org_id, team_id, slug, name, created_at, updated_at
+            SELECT id, org_id, team_id, slug, name, request_defaults, parameter_governance, model_access, labels, created_at, updated_at
             FROM projects
             WHERE org_id = ? AND (created_at, id) {} (?, ?)
             {}
+            AND NOT EXISTS (SELECT 1 FROM json_each(?) l WHERE json_extract(labels, '$."' || l.key || '"') IS NOT l.value)
             ORDER BY created_at {}, id {}
             LIMIT ?
             "#,
@@ -61,6 +98,7 @@ impl SqliteProjectRepo {
             .bind(org_id.to_string())
             .bind(cursor.created_at)
             .bind(cursor.id.to_string())
+            .bind(params.labels.to_json().to_string())
             .bind(fetch_limit)
             .fetch_all(&self.pool)
             .await?;
@@ -69,18 +107,7 @@ impl SqliteProjectRepo {
         let mut items: Vec<Project> = rows
             .into_iter()
             .take(limit as usize)
-            .map(|row| {
-                let team_id: Option<String> = row.col("team_id");
-                Ok(Project {
-                    id: parse_uuid(&row.col::<String>("id"))?,
-                    org_id: parse_uuid(&row.col::<String>("org_id"))?,
-                    team_id: team_id.as_deref().map(parse_uuid).transpose()?,
-                    slug: row.col("slug"),
-                    name: row.col("name"),
-                    created_at: row.col("created_at"),
-                    updated_at: row.col("updated_at"),
-                })
-            })
+            .map(|row| project_from_row(&row))
             .collect::<DbResult<Vec<_>>>()?;
 
         if should_reverse {
@@ -130,6 +157,10 @@ impl ProjectRepo for SqliteProjectRepo {
             team_id: input.team_id,
             slug: input.slug,
             name: input.name,
+            request_defaults: None,
+            parameter_governance: None,
+            model_access: None,
+            labels: Default::default(),
             created_at: now,
             updated_at: now,
         })
@@ -138,7 +169,7 @@ impl ProjectRepo for SqliteProjectRepo {
     async fn get_by_id(&self, id: Uuid) -> DbResult<Option<Project>> {
         let result = query(
             r#"
-            SELECT id, org_id, team_id, slug, name, created_at, updated_at
+            SELECT id, org_id, team_id, slug, name, request_defaults, parameter_governance, model_access, labels, created_at, updated_at
             FROM projects
             WHERE id = ? AND deleted_at IS NULL
             "#,
@@ -148,18 +179,7 @@ impl ProjectRepo for SqliteProjectRepo {
         .await?;
 
         match result {
-            Some(row) => {
-                let team_id: Option<String> = row.col("team_id");
-                Ok(Some(Project {
-                    id: parse_uuid(&row.col::<String>("id"))?,
-                    org_id: parse_uuid(&row.col::<String>("org_id"))?,
-                    team_id: team_id.as_deref().map(parse_uuid).transpose()?,
-                    slug: row.col("slug"),
-                    name: row.col("name"),
-                    created_at: row.col("created_at"),
-                    updated_at: row.col("updated_at"),
-                }))
-            }
+            Some(row) => project_from_row(&row).map(Some),
             None => Ok(None),
         }
     }
@@ -167,7 +187,7 @@ impl ProjectRepo for SqliteProjectRepo {
     async fn get_by_id_and_org(&self, id: Uuid, org_id: Uuid) -> DbResult<Option<Project>> {
         let result = query(
             r#"
-            SELECT id, org_id, team_id, slug, name, created_at, updated_at
+            SELECT id, org_id, team_id, slug, name, request_defaults, parameter_governance, model_access, labels, created_at, updated_at
             FROM projects
             WHERE id = ? AND org_id = ? AND deleted_at IS NULL
             "#,
@@ -178,18 +198,7 @@ impl ProjectRepo for SqliteProjectRepo {
         .await?;
 
         match result {
-            Some(row) => {
-                let team_id: Option<String> = row.col("team_id");
-                Ok(Some(Project {
-                    id: parse_uuid(&row.col::<String>("id"))?,
-                    org_id: parse_uuid(&row.col::<String>("org_id"))?,
-                    team_id: team_id.as_deref().map(parse_uuid).transpose()?,
-                    slug: row.col("slug"),
-                    name: row.col("name"),
-                    created_at: row.col("created_at"),
-                    updated_at: row.col("updated_at"),
-                }))
-            }
+            Some(row) => project_from_row(&row).map(Some),
             None => Ok(None),
         }
     }
@@ -197,7 +206,7 @@ impl ProjectRepo for SqliteProjectRepo {
     async fn get_by_slug(&self, org_id: Uuid, slug: &str) -> DbResult<Option<Project>> {
         let result = query(
             r#"
-            SELECT id, org_id, team_id, slug, name, created_at, updated_at
+            SELECT id, org_id, team_id, slug, name, request_defaults, parameter_governance, model_access, labels, created_at, updated_at
             FROM projects
             WHERE org_id = ? AND slug = ? AND deleted_at IS NULL
             "#,
@@ -208,18 +217,7 @@ impl ProjectRepo for SqliteProjectRepo {
         .await?;
 
         match result {
-            Some(row) => {
-                let team_id: Option<String> = row.col("team_id");
-                Ok(Some(Project {
-                    id: parse_uuid(&row.col::<String>("id"))?,
-                    org_id: parse_uuid(&row.col::<String>("org_id"))?,
-                    team_id: team_id.as_deref().map(parse_uuid).transpose()?,
-                    slug: row.col("slug"),
-                    name: row.col("name"),
-                    created_at: row.col("created_at"),
-                    updated_at: row.col("updated_at"),
-                }))
-            }
+            Some(row) => project_from_row(&row).map(Some),
             None => Ok(None),
         }
     }
@@ -239,17 +237,19 @@ impl ProjectRepo for SqliteProjectRepo {
         // First page (no cursor provided)
         let sql = if params.include_deleted {
             r#"
-            SELECT id, org_id, team_id, slug, name, created_at, updated_at
+            SELECT id, org_id, team_id, slug, name, request_defaults, parameter_governance, model_access, labels, created_at, updated_at
             FROM projects
             WHERE org_id = ?
+            AND NOT EXISTS (SELECT 1 FROM json_each(?) l WHERE json_extract(labels, '$."' || l.key || '"') IS NOT l.value)
             ORDER BY created_at DESC, id DESC
             LIMIT ?
             "#
         } else {
             r#"
-            SELECT id, org_id, team_id, slug, name, created_at, updated_at
+            SELECT id, org_id, team_id, slug, name, request_defaults, parameter_governance, model_access, labels, created_at, updated_at
             FROM projects
             WHERE org_id = ? AND deleted_at IS NULL
+            AND NOT EXISTS (SELECT 1 FROM json_each(?) l WHERE json_extract(labels, '$."' || l.key || '"') IS NOT l.value)
             ORDER BY created_at DESC, id DESC
             LIMIT ?
             "#
@@ -257,6 +257,7 @@ impl ProjectRepo for SqliteProjectRepo {
 
         let rows = query(sql)
             .bind(org_id.to_string())
+            .bind(params.labels.to_json().to_string())
             .bind(fetch_limit)
             .fetch_all(&self.pool)
             .await?;
@@ -265,18 +266,7 @@ impl ProjectRepo for SqliteProjectRepo {
         let items: Vec<Project> = rows
             .into_iter()
             .take(limit as usize)
-            .map(|row| {
-                let team_id: Option<String> = row.col("team_id");
-                Ok(Project {
-                    id: parse_uuid(&row.col::<String>("id"))?,
-                    org_id: parse_uuid(&row.col::<String>("org_id"))?,
-                    team_id: team_id.as_deref().map(parse_uuid).transpose()?,
-                    slug: row.col("slug"),
-                    name: row.col("name"),
-                    created_at: row.col("created_at"),
-                    updated_at: row.col("updated_at"),
-                })
-            })
+            .map(|row| project_from_row(&row))
             .collect::<DbResult<Vec<_>>>()?;
 
         // Generate cursors for pagination
@@ -329,8 +319,9 @@ impl ProjectRepo for SqliteProjectRepo {
     async fn update(&self, id: Uuid, input: UpdateProject) -> DbResult<Project> {
         let has_name_update = input.name.is_some();
         let has_team_update = input.team_id.is_some();
+        let has_defaults_update = input.request_defaults.is_some();
 
-        if !has_name_update && !has_team_update {
+        if !has_name_update && !has_team_update && !has_defaults_update {
             return self.get_by_id(id).await?.ok_or(DbError::NotFound);
         }
 
@@ -344,6 +335,9 @@ impl ProjectRepo for SqliteProjectRepo {
         if has_team_update {
             set_clauses.push("team_id = ?");
         }
+        if has_defaults_update {
+            set_clauses.push("request_defaults = ?");
+        }
 
         let sql = format!(
             "UPDATE projects SET {} WHERE id = ? AND deleted_at IS NULL",
@@ -358,6 +352,13 @@ impl ProjectRepo for SqliteProjectRepo {
         if let Some(ref team_id_opt) = input.team_id {
             query_builder = query_builder.bind(team_id_opt.map(|id| id.to_string()));
         }
+        if let Some(ref defaults) = input.request_defaults {
+            query_builder = query_builder.bind(
+                defaults
+                    .as_ref()
+                    .and_then(|d| serde_json::to_string(d).ok()),
+            );
+        }
 
         let result = query_builder
             .bind(id.to_string())
@@ -371,6 +372,95 @@ impl ProjectRepo for SqliteProjectRepo {
         self.get_by_id(id).await?.ok_or(DbError::NotFound)
     }
 
+    async fn set_parameter_governance(
+        &self,
+        id: Uuid,
+        governance: Option<&ParameterGovernance>,
+    ) -> DbResult<Project> {
+        let now = truncate_to_millis(chrono::Utc::now());
+        let governance = governance
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| {
+                DbError::Internal(format!("failed to serialize parameter_governance: {e}"))
+            })?;
+
+        let result = query(
+            r#"
+            UPDATE projects
+            SET parameter_governance = ?, updated_at = ?
+            WHERE id = ? AND deleted_at IS NULL
+            "#,
+        )
+        .bind(governance)
+        .bind(now)
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        self.get_by_id(id).await?.ok_or(DbError::NotFound)
+    }
+
+    async fn set_model_access(
+        &self,
+        id: Uuid,
+        policy: Option<&ModelAccessPolicy>,
+    ) -> DbResult<Project> {
+        let now = truncate_to_millis(chrono::Utc::now());
+        let policy = policy
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| DbError::Internal(format!("failed to serialize model_access: {e}")))?;
+
+        let result = query(
+            r#"
+            UPDATE projects
+            SET model_access = ?, updated_at = ?
+            WHERE id = ? AND deleted_at IS NULL
+            "#,
+        )
+        .bind(policy)
+        .bind(now)
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        self.get_by_id(id).await?.ok_or(DbError::NotFound)
+    }
+
+    async fn set_labels(&self, id: Uuid, labels: &BTreeMap<String, String>) -> DbResult<Project> {
+        let now = truncate_to_millis(chrono::Utc::now());
+        let labels = serde_json::to_string(labels)
+            .map_err(|e| DbError::Internal(format!("failed to serialize labels: {e}")))?;
+
+        let result = query(
+            r#"
+            UPDATE projects
+            SET labels = ?, updated_at = ?
+            WHERE id = ? AND deleted_at IS NULL
+            "#,
+        )
+        .bind(labels)
+        .bind(now)
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        self.get_by_id(id).await?.ok_or(DbError::NotFound)
+    }
+
     async fn delete(&self, id: Uuid) -> DbResult<()> {
         let now = truncate_to_millis(chrono::Utc::now());
 
@@ -435,6 +525,10 @@ mod tests {
                 team_id TEXT,
                 slug TEXT NOT NULL,
                 name TEXT NOT NULL,
+                request_defaults TEXT,
+                parameter_governance TEXT,
+                model_access TEXT,
+                labels TEXT NOT NULL DEFAULT '{}',
                 created_at TEXT NOT NULL DEFAULT (datetime('now')),
                 updated_at TEXT NOT NULL DEFAULT (datetime('now')),
                 deleted_at TEXT,
@@ -831,6 +925,7 @@ mod tests {
                 UpdateProject {
                     name: Some("Updated Name".to_string()),
                     team_id: None,
+                    request_defaults: None,
                 },
             )
             .await
@@ -859,6 +954,7 @@ mod tests {
                 UpdateProject {
                     name: None,
                     team_id: None,
+                    request_defaults: None,
                 },
             )
             .await
@@ -878,6 +974,7 @@ mod tests {
                 UpdateProject {
                     name: Some("New Name".to_string()),
                     team_id: None,
+                    request_defaults: None,
                 },
             )
             .await;
@@ -1056,6 +1153,7 @@ mod tests {
                 UpdateProject {
                     name: Some("New Name".to_string()),
                     team_id: None,
+                    request_defaults: None,
                 },
             )
             .await;
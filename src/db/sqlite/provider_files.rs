@@ -0,0 +1,112 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use super::{
+    backend::{Pool, Row, RowExt, map_unique_violation, query},
+    common::parse_uuid,
+};
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{ProviderFileRepo, truncate_to_millis},
+    },
+    models::ProviderFile,
+};
+
+const COLUMNS: &str = "id, file_id, provider, provider_file_id, created_at";
+
+pub struct SqliteProviderFileRepo {
+    pool: Pool,
+}
+
+impl SqliteProviderFileRepo {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    fn parse_provider_file(row: &Row) -> DbResult<ProviderFile> {
+        Ok(ProviderFile {
+            id: parse_uuid(&row.col::<String>("id"))?,
+            file_id: parse_uuid(&row.col::<String>("file_id"))?,
+            provider: row.col("provider"),
+            provider_file_id: row.col("provider_file_id"),
+            created_at: row.col("created_at"),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl ProviderFileRepo for SqliteProviderFileRepo {
+    async fn create(
+        &self,
+        file_id: Uuid,
+        provider: &str,
+        provider_file_id: &str,
+    ) -> DbResult<ProviderFile> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+
+        query(
+            r#"
+            INSERT INTO provider_files (id, file_id, provider, provider_file_id, created_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(id.to_string())
+        .bind(file_id.to_string())
+        .bind(provider)
+        .bind(provider_file_id)
+        .bind(now)
+        .execute(&self.pool)
+        .await
+        .map_err(map_unique_violation(format!(
+            "File '{file_id}' already has a copy at provider '{provider}'"
+        )))?;
+
+        Ok(ProviderFile {
+            id,
+            file_id,
+            provider: provider.to_string(),
+            provider_file_id: provider_file_id.to_string(),
+            created_at: now,
+        })
+    }
+
+    async fn get(&self, file_id: Uuid, provider: &str) -> DbResult<Option<ProviderFile>> {
+        let sql =
+            format!("SELECT {COLUMNS} FROM provider_files WHERE file_id = ? AND provider = ?");
+        let row = query(&sql)
+            .bind(file_id.to_string())
+            .bind(provider)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|r| Self::parse_provider_file(&r)).transpose()
+    }
+
+    async fn list_by_file(&self, file_id: Uuid) -> DbResult<Vec<ProviderFile>> {
+        let sql = format!(
+            "SELECT {COLUMNS} FROM provider_files WHERE file_id = ? ORDER BY created_at ASC, id ASC"
+        );
+        let rows = query(&sql)
+            .bind(file_id.to_string())
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter().map(Self::parse_provider_file).collect()
+    }
+
+    async fn delete(&self, id: Uuid) -> DbResult<()> {
+        let result = query("DELETE FROM provider_files WHERE id = ?")
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+        Ok(())
+    }
+}
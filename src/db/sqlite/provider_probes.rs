@@ -0,0 +1,128 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::backend::{Pool, RowExt, query};
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{ProviderProbeRepo, truncate_to_millis},
+    },
+    models::{CreateProviderProbeResult, ProbeBucketStats, ProviderProbeResult},
+};
+
+pub struct SqliteProviderProbeRepo {
+    pool: Pool,
+}
+
+impl SqliteProviderProbeRepo {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl ProviderProbeRepo for SqliteProviderProbeRepo {
+    async fn create(&self, input: CreateProviderProbeResult) -> DbResult<ProviderProbeResult> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+
+        query(
+            r#"
+            INSERT INTO provider_probe_results (
+                id, provider, model, success, correct, latency_ms,
+                input_tokens, output_tokens, error, created_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(id.to_string())
+        .bind(&input.provider)
+        .bind(&input.model)
+        .bind(input.success)
+        .bind(input.correct)
+        .bind(input.latency_ms)
+        .bind(input.input_tokens)
+        .bind(input.output_tokens)
+        .bind(&input.error)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(ProviderProbeResult {
+            id,
+            provider: input.provider,
+            model: input.model,
+            success: input.success,
+            correct: input.correct,
+            latency_ms: input.latency_ms,
+            input_tokens: input.input_tokens,
+            output_tokens: input.output_tokens,
+            error: input.error,
+            created_at: now,
+        })
+    }
+
+    async fn history(
+        &self,
+        provider: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        bucket_secs: i64,
+    ) -> DbResult<Vec<ProbeBucketStats>> {
+        let rows = query(
+            r#"
+            SELECT
+                (CAST(strftime('%s', created_at) AS INTEGER) / ?) * ? as bucket,
+                model,
+                COUNT(*) as probe_count,
+                SUM(success) as success_count,
+                SUM(correct) as correct_count,
+                AVG(CASE WHEN success = 1 THEN latency_ms END) as avg_latency_ms,
+                MAX(CASE WHEN success = 1 THEN latency_ms END) as max_latency_ms,
+                AVG(CASE WHEN success = 1 THEN output_tokens END) as avg_output_tokens
+            FROM provider_probe_results
+            WHERE provider = ? AND created_at >= ? AND created_at < ?
+            GROUP BY bucket, model
+            ORDER BY bucket ASC, model ASC
+            "#,
+        )
+        .bind(bucket_secs)
+        .bind(bucket_secs)
+        .bind(provider)
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                let bucket: i64 = row.col("bucket");
+                Ok(ProbeBucketStats {
+                    bucket_start: DateTime::from_timestamp(bucket, 0).ok_or_else(|| {
+                        DbError::Internal(format!("Invalid probe bucket timestamp: {bucket}"))
+                    })?,
+                    model: row.col("model"),
+                    probe_count: row.col("probe_count"),
+                    success_count: row.col("success_count"),
+                    correct_count: row.col("correct_count"),
+                    avg_latency_ms: row.col("avg_latency_ms"),
+                    max_latency_ms: row.col("max_latency_ms"),
+                    avg_output_tokens: row.col("avg_output_tokens"),
+                })
+            })
+            .collect()
+    }
+
+    async fn delete_before(&self, provider: &str, cutoff: DateTime<Utc>) -> DbResult<u64> {
+        let result =
+            query("DELETE FROM provider_probe_results WHERE provider = ? AND created_at < ?")
+                .bind(provider)
+                .bind(cutoff)
+                .execute(&self.pool)
+                .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,136 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::de::DeserializeOwned;
+use uuid::Uuid;
+
+use super::{
+    backend::{Pool, Row, RowExt, query},
+    common::parse_uuid,
+};
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{ProviderSettingsRepo, truncate_to_millis},
+    },
+    models::{ProviderSettings, UpdateProviderSettings},
+};
+
+const COLUMNS: &str = "id, provider_name, enabled, circuit_breaker, retry, max_concurrent, \
+                       updated_by, created_at, updated_at";
+
+pub struct SqliteProviderSettingsRepo {
+    pool: Pool,
+}
+
+impl SqliteProviderSettingsRepo {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    fn parse_json<T: DeserializeOwned>(row: &Row, column: &str) -> DbResult<Option<T>> {
+        row.col::<Option<String>>(column)
+            .map(|json| serde_json::from_str(&json))
+            .transpose()
+            .map_err(|e| DbError::Internal(format!("failed to deserialize {column}: {e}")))
+    }
+
+    fn parse_settings(row: &Row) -> DbResult<ProviderSettings> {
+        Ok(ProviderSettings {
+            id: parse_uuid(&row.col::<String>("id"))?,
+            provider_name: row.col("provider_name"),
+            enabled: row.col::<Option<i32>>("enabled").map(|v| v != 0),
+            circuit_breaker: Self::parse_json(row, "circuit_breaker")?,
+            retry: Self::parse_json(row, "retry")?,
+            max_concurrent: row.col::<Option<i64>>("max_concurrent").map(|v| v as u32),
+            updated_by: row
+                .col::<Option<String>>("updated_by")
+                .map(|s| parse_uuid(&s))
+                .transpose()?,
+            created_at: row.col("created_at"),
+            updated_at: row.col("updated_at"),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl ProviderSettingsRepo for SqliteProviderSettingsRepo {
+    async fn get(&self, provider_name: &str) -> DbResult<Option<ProviderSettings>> {
+        let sql = format!("SELECT {COLUMNS} FROM provider_settings WHERE provider_name = ?");
+        let row = query(&sql)
+            .bind(provider_name)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|r| Self::parse_settings(&r)).transpose()
+    }
+
+    async fn list(&self) -> DbResult<Vec<ProviderSettings>> {
+        let sql = format!("SELECT {COLUMNS} FROM provider_settings ORDER BY provider_name ASC");
+        let rows = query(&sql).fetch_all(&self.pool).await?;
+
+        rows.iter().map(Self::parse_settings).collect()
+    }
+
+    async fn upsert(
+        &self,
+        provider_name: &str,
+        input: UpdateProviderSettings,
+        updated_by: Option<Uuid>,
+    ) -> DbResult<ProviderSettings> {
+        let now = truncate_to_millis(Utc::now());
+        let circuit_breaker = input
+            .circuit_breaker
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| DbError::Internal(format!("failed to serialize circuit_breaker: {e}")))?;
+        let retry = input
+            .retry
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| DbError::Internal(format!("failed to serialize retry: {e}")))?;
+
+        let sql = format!(
+            r#"
+            INSERT INTO provider_settings (
+                id, provider_name, enabled, circuit_breaker, retry, max_concurrent,
+                updated_by, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT (provider_name) DO UPDATE SET
+                enabled = excluded.enabled,
+                circuit_breaker = excluded.circuit_breaker,
+                retry = excluded.retry,
+                max_concurrent = excluded.max_concurrent,
+                updated_by = excluded.updated_by,
+                updated_at = excluded.updated_at
+            RETURNING {COLUMNS}
+            "#
+        );
+        let row = query(&sql)
+            .bind(Uuid::new_v4().to_string())
+            .bind(provider_name)
+            .bind(input.enabled.map(|v| if v { 1 } else { 0 }))
+            .bind(circuit_breaker)
+            .bind(retry)
+            .bind(input.max_concurrent.map(i64::from))
+            .bind(updated_by.map(|id| id.to_string()))
+            .bind(now)
+            .bind(now)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Self::parse_settings(&row)
+    }
+
+    async fn delete(&self, provider_name: &str) -> DbResult<bool> {
+        let result = query("DELETE FROM provider_settings WHERE provider_name = ?")
+            .bind(provider_name)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,193 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use super::{
+    backend::{Pool, Row, RowExt, begin, query},
+    common::parse_uuid,
+};
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{DateRange, ProviderUsageImportRepo, truncate_to_millis},
+    },
+    models::{CreateProviderUsageImport, ImportedUsage, ImportedUsageRow, ProviderUsageImport},
+};
+
+const IMPORT_COLUMNS: &str =
+    "id, provider, source, method, start_date, end_date, row_count, created_at";
+
+pub struct SqliteProviderUsageImportRepo {
+    pool: Pool,
+}
+
+impl SqliteProviderUsageImportRepo {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    fn parse_import(row: &Row) -> DbResult<ProviderUsageImport> {
+        Ok(ProviderUsageImport {
+            id: parse_uuid(&row.col::<String>("id"))?,
+            provider: row.col("provider"),
+            source: row
+                .col::<String>("source")
+                .parse()
+                .map_err(DbError::Internal)?,
+            method: row
+                .col::<String>("method")
+                .parse()
+                .map_err(DbError::Internal)?,
+            start_date: row.col("start_date"),
+            end_date: row.col("end_date"),
+            row_count: row.col("row_count"),
+            created_at: row.col("created_at"),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl ProviderUsageImportRepo for SqliteProviderUsageImportRepo {
+    async fn record_import(
+        &self,
+        input: CreateProviderUsageImport,
+        usage: &[ImportedUsage],
+    ) -> DbResult<ProviderUsageImport> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+        let row_count = usage.len() as i64;
+
+        let mut tx = begin(&self.pool).await?;
+
+        let sql = format!(
+            "INSERT INTO provider_usage_imports ({IMPORT_COLUMNS}) VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
+        );
+        query(&sql)
+            .bind(id.to_string())
+            .bind(&input.provider)
+            .bind(input.source.as_str())
+            .bind(input.method.as_str())
+            .bind(input.start_date)
+            .bind(input.end_date)
+            .bind(row_count)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+
+        query("DELETE FROM provider_usage WHERE provider = ? AND date >= ? AND date <= ?")
+            .bind(&input.provider)
+            .bind(input.start_date)
+            .bind(input.end_date)
+            .execute(&mut *tx)
+            .await?;
+
+        for usage in usage {
+            query(
+                r#"
+                INSERT INTO provider_usage (
+                    provider, date, model, import_id, request_count, input_tokens,
+                    output_tokens, cost_microcents
+                )
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&input.provider)
+            .bind(usage.date)
+            .bind(&usage.model)
+            .bind(id.to_string())
+            .bind(usage.request_count)
+            .bind(usage.input_tokens)
+            .bind(usage.output_tokens)
+            .bind(usage.cost_microcents)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(ProviderUsageImport {
+            id,
+            provider: input.provider,
+            source: input.source,
+            method: input.method,
+            start_date: input.start_date,
+            end_date: input.end_date,
+            row_count,
+            created_at: now,
+        })
+    }
+
+    async fn list_imports(&self, provider: Option<&str>) -> DbResult<Vec<ProviderUsageImport>> {
+        let sql = format!(
+            "SELECT {IMPORT_COLUMNS} FROM provider_usage_imports \
+             WHERE (? IS NULL OR provider = ?) \
+             ORDER BY created_at DESC, id DESC"
+        );
+        let rows = query(&sql)
+            .bind(provider)
+            .bind(provider)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter().map(Self::parse_import).collect()
+    }
+
+    async fn delete_import(&self, id: Uuid) -> DbResult<bool> {
+        let mut tx = begin(&self.pool).await?;
+
+        // Usage rows are removed explicitly; the cascade only fires on pools
+        // with `PRAGMA foreign_keys` enabled, which the test harness lacks.
+        query("DELETE FROM provider_usage WHERE import_id = ?")
+            .bind(id.to_string())
+            .execute(&mut *tx)
+            .await?;
+        let result = query("DELETE FROM provider_usage_imports WHERE id = ?")
+            .bind(id.to_string())
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list_usage(
+        &self,
+        range: DateRange,
+        provider: Option<&str>,
+    ) -> DbResult<Vec<ImportedUsageRow>> {
+        let rows = query(
+            r#"
+            SELECT provider, date, model, import_id, request_count, input_tokens,
+                   output_tokens, cost_microcents
+            FROM provider_usage
+            WHERE date >= ? AND date <= ?
+              AND (? IS NULL OR provider = ?)
+            ORDER BY provider ASC, date ASC, model ASC
+            "#,
+        )
+        .bind(range.start)
+        .bind(range.end)
+        .bind(provider)
+        .bind(provider)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(ImportedUsageRow {
+                    provider: row.col("provider"),
+                    import_id: parse_uuid(&row.col::<String>("import_id"))?,
+                    usage: ImportedUsage {
+                        date: row.col("date"),
+                        model: row.col("model"),
+                        request_count: row.col("request_count"),
+                        input_tokens: row.col("input_tokens"),
+                        output_tokens: row.col("output_tokens"),
+                        cost_microcents: row.col("cost_microcents"),
+                    },
+                })
+            })
+            .collect()
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use async_trait::async_trait;
 use uuid::Uuid;
 
@@ -81,6 +83,8 @@ impl SqliteDynamicProviderRepo {
                     DbError::Internal(format!("failed to deserialize sovereignty metadata: {e}"))
                 })?,
             is_enabled: row.col::<i32>("is_enabled") != 0,
+            labels: serde_json::from_str(&row.col::<String>("labels"))
+                .map_err(|e| DbError::Internal(format!("failed to deserialize labels: {e}")))?,
             created_at: row.col("created_at"),
             updated_at: row.col("updated_at"),
         })
@@ -101,10 +105,11 @@ impl SqliteDynamicProviderRepo {
         let sql = format!(
             r#"
             SELECT id, owner_type, owner_id, name, provider_type, base_url,
-                   api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                   api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
             FROM dynamic_providers
             WHERE owner_type = 'organization' AND owner_id = ?
             AND (created_at, id) {} (?, ?)
+            AND NOT EXISTS (SELECT 1 FROM json_each(?) l WHERE json_extract(labels, '$."' || l.key || '"') IS NOT l.value)
             ORDER BY created_at {}, id {}
             LIMIT ?
             "#,
@@ -115,6 +120,7 @@ impl SqliteDynamicProviderRepo {
             .bind(org_id.to_string())
             .bind(cursor.created_at)
             .bind(cursor.id.to_string())
+            .bind(params.labels.to_json().to_string())
             .bind(fetch_limit)
             .fetch_all(&self.pool)
             .await?;
@@ -153,10 +159,11 @@ impl SqliteDynamicProviderRepo {
         let sql = format!(
             r#"
             SELECT id, owner_type, owner_id, name, provider_type, base_url,
-                   api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                   api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
             FROM dynamic_providers
             WHERE owner_type = 'project' AND owner_id = ?
             AND (created_at, id) {} (?, ?)
+            AND NOT EXISTS (SELECT 1 FROM json_each(?) l WHERE json_extract(labels, '$."' || l.key || '"') IS NOT l.value)
             ORDER BY created_at {}, id {}
             LIMIT ?
             "#,
@@ -167,6 +174,7 @@ impl SqliteDynamicProviderRepo {
             .bind(project_id.to_string())
             .bind(cursor.created_at)
             .bind(cursor.id.to_string())
+            .bind(params.labels.to_json().to_string())
             .bind(fetch_limit)
             .fetch_all(&self.pool)
             .await?;
@@ -205,10 +213,11 @@ impl SqliteDynamicProviderRepo {
         let sql = format!(
             r#"
             SELECT id, owner_type, owner_id, name, provider_type, base_url,
-                   api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                   api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
             FROM dynamic_providers
             WHERE owner_type = 'team' AND owner_id = ?
             AND (created_at, id) {} (?, ?)
+            AND NOT EXISTS (SELECT 1 FROM json_each(?) l WHERE json_extract(labels, '$."' || l.key || '"') IS NOT l.value)
             ORDER BY created_at {}, id {}
             LIMIT ?
             "#,
@@ -219,6 +228,7 @@ impl SqliteDynamicProviderRepo {
             .bind(team_id.to_string())
             .bind(cursor.created_at)
             .bind(cursor.id.to_string())
+            .bind(params.labels.to_json().to_string())
             .bind(fetch_limit)
             .fetch_all(&self.pool)
             .await?;
@@ -257,10 +267,11 @@ impl SqliteDynamicProviderRepo {
         let sql = format!(
             r#"
             SELECT id, owner_type, owner_id, name, provider_type, base_url,
-                   api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                   api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
             FROM dynamic_providers
             WHERE owner_type = 'user' AND owner_id = ?
             AND (created_at, id) {} (?, ?)
+            AND NOT EXISTS (SELECT 1 FROM json_each(?) l WHERE json_extract(labels, '$."' || l.key || '"') IS NOT l.value)
             ORDER BY created_at {}, id {}
             LIMIT ?
             "#,
@@ -271,6 +282,7 @@ impl SqliteDynamicProviderRepo {
             .bind(user_id.to_string())
             .bind(cursor.created_at)
             .bind(cursor.id.to_string())
+            .bind(params.labels.to_json().to_string())
             .bind(fetch_limit)
             .fetch_all(&self.pool)
             .await?;
@@ -353,6 +365,7 @@ impl DynamicProviderRepo for SqliteDynamicProviderRepo {
             config: input.config,
             models: input.models.unwrap_or_default(),
             sovereignty: input.sovereignty,
+            labels: Default::default(),
             is_enabled: true,
             created_at: now,
             updated_at: now,
@@ -363,7 +376,7 @@ impl DynamicProviderRepo for SqliteDynamicProviderRepo {
         let row = query(
             r#"
             SELECT id, owner_type, owner_id, name, provider_type, base_url,
-                   api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                   api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
             FROM dynamic_providers
             WHERE id = ?
             "#,
@@ -385,7 +398,7 @@ impl DynamicProviderRepo for SqliteDynamicProviderRepo {
         let row = query(
             r#"
             SELECT id, owner_type, owner_id, name, provider_type, base_url,
-                   api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                   api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
             FROM dynamic_providers
             WHERE owner_type = ? AND owner_id = ? AND name = ?
             "#,
@@ -418,14 +431,16 @@ impl DynamicProviderRepo for SqliteDynamicProviderRepo {
         let rows = query(
             r#"
             SELECT id, owner_type, owner_id, name, provider_type, base_url,
-                   api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                   api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
             FROM dynamic_providers
             WHERE owner_type = 'organization' AND owner_id = ?
+            AND NOT EXISTS (SELECT 1 FROM json_each(?) l WHERE json_extract(labels, '$."' || l.key || '"') IS NOT l.value)
             ORDER BY created_at DESC, id DESC
             LIMIT ?
             "#,
         )
         .bind(org_id.to_string())
+        .bind(params.labels.to_json().to_string())
         .bind(fetch_limit)
         .fetch_all(&self.pool)
         .await?;
@@ -478,14 +493,16 @@ impl DynamicProviderRepo for SqliteDynamicProviderRepo {
         let rows = query(
             r#"
             SELECT id, owner_type, owner_id, name, provider_type, base_url,
-                   api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                   api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
             FROM dynamic_providers
             WHERE owner_type = 'team' AND owner_id = ?
+            AND NOT EXISTS (SELECT 1 FROM json_each(?) l WHERE json_extract(labels, '$."' || l.key || '"') IS NOT l.value)
             ORDER BY created_at DESC, id DESC
             LIMIT ?
             "#,
         )
         .bind(team_id.to_string())
+        .bind(params.labels.to_json().to_string())
         .bind(fetch_limit)
         .fetch_all(&self.pool)
         .await?;
@@ -538,14 +555,16 @@ impl DynamicProviderRepo for SqliteDynamicProviderRepo {
         let rows = query(
             r#"
             SELECT id, owner_type, owner_id, name, provider_type, base_url,
-                   api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                   api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
             FROM dynamic_providers
             WHERE owner_type = 'project' AND owner_id = ?
+            AND NOT EXISTS (SELECT 1 FROM json_each(?) l WHERE json_extract(labels, '$."' || l.key || '"') IS NOT l.value)
             ORDER BY created_at DESC, id DESC
             LIMIT ?
             "#,
         )
         .bind(project_id.to_string())
+        .bind(params.labels.to_json().to_string())
         .bind(fetch_limit)
         .fetch_all(&self.pool)
         .await?;
@@ -598,14 +617,16 @@ impl DynamicProviderRepo for SqliteDynamicProviderRepo {
         let rows = query(
             r#"
             SELECT id, owner_type, owner_id, name, provider_type, base_url,
-                   api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                   api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
             FROM dynamic_providers
             WHERE owner_type = 'user' AND owner_id = ?
+            AND NOT EXISTS (SELECT 1 FROM json_each(?) l WHERE json_extract(labels, '$."' || l.key || '"') IS NOT l.value)
             ORDER BY created_at DESC, id DESC
             LIMIT ?
             "#,
         )
         .bind(user_id.to_string())
+        .bind(params.labels.to_json().to_string())
         .bind(fetch_limit)
         .fetch_all(&self.pool)
         .await?;
@@ -654,7 +675,7 @@ impl DynamicProviderRepo for SqliteDynamicProviderRepo {
             let sql = format!(
                 r#"
                 SELECT id, owner_type, owner_id, name, provider_type, base_url,
-                       api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                       api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
                 FROM dynamic_providers
                 WHERE owner_type = 'user' AND owner_id = ? AND is_enabled = 1
                 AND (created_at, id) {} (?, ?)
@@ -694,7 +715,7 @@ impl DynamicProviderRepo for SqliteDynamicProviderRepo {
         let rows = query(
             r#"
             SELECT id, owner_type, owner_id, name, provider_type, base_url,
-                   api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                   api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
             FROM dynamic_providers
             WHERE owner_type = 'user' AND owner_id = ? AND is_enabled = 1
             ORDER BY created_at DESC, id DESC
@@ -735,7 +756,7 @@ impl DynamicProviderRepo for SqliteDynamicProviderRepo {
             let sql = format!(
                 r#"
                 SELECT id, owner_type, owner_id, name, provider_type, base_url,
-                       api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                       api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
                 FROM dynamic_providers
                 WHERE owner_type = 'organization' AND owner_id = ? AND is_enabled = 1
                 AND (created_at, id) {} (?, ?)
@@ -775,7 +796,7 @@ impl DynamicProviderRepo for SqliteDynamicProviderRepo {
         let rows = query(
             r#"
             SELECT id, owner_type, owner_id, name, provider_type, base_url,
-                   api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                   api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
             FROM dynamic_providers
             WHERE owner_type = 'organization' AND owner_id = ? AND is_enabled = 1
             ORDER BY created_at DESC, id DESC
@@ -816,7 +837,7 @@ impl DynamicProviderRepo for SqliteDynamicProviderRepo {
             let sql = format!(
                 r#"
                 SELECT id, owner_type, owner_id, name, provider_type, base_url,
-                       api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                       api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
                 FROM dynamic_providers
                 WHERE owner_type = 'project' AND owner_id = ? AND is_enabled = 1
                 AND (created_at, id) {} (?, ?)
@@ -856,7 +877,7 @@ impl DynamicProviderRepo for SqliteDynamicProviderRepo {
         let rows = query(
             r#"
             SELECT id, owner_type, owner_id, name, provider_type, base_url,
-                   api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                   api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
             FROM dynamic_providers
             WHERE owner_type = 'project' AND owner_id = ? AND is_enabled = 1
             ORDER BY created_at DESC, id DESC
@@ -897,7 +918,7 @@ impl DynamicProviderRepo for SqliteDynamicProviderRepo {
             let sql = format!(
                 r#"
                 SELECT id, owner_type, owner_id, name, provider_type, base_url,
-                       api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                       api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
                 FROM dynamic_providers
                 WHERE owner_type = 'team' AND owner_id = ? AND is_enabled = 1
                 AND (created_at, id) {} (?, ?)
@@ -937,7 +958,7 @@ impl DynamicProviderRepo for SqliteDynamicProviderRepo {
         let rows = query(
             r#"
             SELECT id, owner_type, owner_id, name, provider_type, base_url,
-                   api_key_secret_ref, config, models, sovereignty, is_enabled, created_at, updated_at
+                   api_key_secret_ref, config, models, sovereignty, is_enabled, labels, created_at, updated_at
             FROM dynamic_providers
             WHERE owner_type = 'team' AND owner_id = ? AND is_enabled = 1
             ORDER BY created_at DESC, id DESC
@@ -1047,6 +1068,35 @@ impl DynamicProviderRepo for SqliteDynamicProviderRepo {
         self.get_by_id(id).await?.ok_or(DbError::NotFound)
     }
 
+    async fn set_labels(
+        &self,
+        id: Uuid,
+        labels: &BTreeMap<String, String>,
+    ) -> DbResult<DynamicProvider> {
+        let now = truncate_to_millis(chrono::Utc::now());
+        let labels = serde_json::to_string(labels)
+            .map_err(|e| DbError::Internal(format!("failed to serialize labels: {e}")))?;
+
+        let result = query(
+            r#"
+            UPDATE dynamic_providers
+            SET labels = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(labels)
+        .bind(now)
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        self.get_by_id(id).await?.ok_or(DbError::NotFound)
+    }
+
     async fn delete(&self, id: Uuid) -> DbResult<()> {
         query("DELETE FROM dynamic_providers WHERE id = ?")
             .bind(id.to_string())
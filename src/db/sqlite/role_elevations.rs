@@ -0,0 +1,291 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::{
+    backend::{Pool, Row, RowExt, map_unique_violation, query},
+    common::parse_uuid,
+};
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{
+            CursorDirection, ListParams, ListResult, PageCursors, RoleElevationRepo,
+            cursor_from_row, truncate_to_millis,
+        },
+    },
+    models::{ElevationStatus, NewRoleElevation, RoleElevation},
+};
+
+const COLUMNS: &str = "id, org_id, user_id, role, previous_role, justification, duration_hours, \
+                       status, requested_by, decided_by, decision_note, decided_at, expires_at, \
+                       ended_at, ended_by, created_at, updated_at";
+
+const ACTIVE_CONFLICT: &str = "The member already has a pending or active elevation";
+
+pub struct SqliteRoleElevationRepo {
+    pool: Pool,
+}
+
+impl SqliteRoleElevationRepo {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    fn parse_elevation(row: &Row) -> DbResult<RoleElevation> {
+        let parse_optional_uuid = |col: &str| {
+            row.col::<Option<String>>(col)
+                .map(|s| parse_uuid(&s))
+                .transpose()
+        };
+
+        Ok(RoleElevation {
+            id: parse_uuid(&row.col::<String>("id"))?,
+            org_id: parse_uuid(&row.col::<String>("org_id"))?,
+            user_id: parse_uuid(&row.col::<String>("user_id"))?,
+            role: row.col("role"),
+            previous_role: row.col("previous_role"),
+            justification: row.col("justification"),
+            duration_hours: row.col("duration_hours"),
+            status: row
+                .col::<String>("status")
+                .parse()
+                .map_err(DbError::Internal)?,
+            requested_by: parse_optional_uuid("requested_by")?,
+            decided_by: parse_optional_uuid("decided_by")?,
+            decision_note: row.col("decision_note"),
+            decided_at: row.col("decided_at"),
+            expires_at: row.col("expires_at"),
+            ended_at: row.col("ended_at"),
+            ended_by: parse_optional_uuid("ended_by")?,
+            created_at: row.col("created_at"),
+            updated_at: row.col("updated_at"),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl RoleElevationRepo for SqliteRoleElevationRepo {
+    async fn create(&self, input: NewRoleElevation) -> DbResult<RoleElevation> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+
+        query(
+            r#"
+            INSERT INTO role_elevations (
+                id, org_id, user_id, role, justification, duration_hours, status,
+                requested_by, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, 'pending', ?, ?, ?)
+            "#,
+        )
+        .bind(id.to_string())
+        .bind(input.org_id.to_string())
+        .bind(input.user_id.to_string())
+        .bind(&input.role)
+        .bind(&input.justification)
+        .bind(input.duration_hours)
+        .bind(input.requested_by.map(|id| id.to_string()))
+        .bind(now)
+        .bind(now)
+        .execute(&self.pool)
+        .await
+        .map_err(map_unique_violation(ACTIVE_CONFLICT))?;
+
+        Ok(RoleElevation {
+            id,
+            org_id: input.org_id,
+            user_id: input.user_id,
+            role: input.role,
+            previous_role: None,
+            justification: input.justification,
+            duration_hours: input.duration_hours,
+            status: ElevationStatus::Pending,
+            requested_by: input.requested_by,
+            decided_by: None,
+            decision_note: None,
+            decided_at: None,
+            expires_at: None,
+            ended_at: None,
+            ended_by: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<RoleElevation>> {
+        let sql = format!("SELECT {COLUMNS} FROM role_elevations WHERE id = ?");
+        let row = query(&sql)
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|r| Self::parse_elevation(&r)).transpose()
+    }
+
+    async fn list_by_org(
+        &self,
+        org_id: Uuid,
+        params: ListParams,
+    ) -> DbResult<ListResult<RoleElevation>> {
+        let limit = params.limit.unwrap_or(100);
+        let fetch_limit = limit + 1;
+
+        let (comparison, order, should_reverse) =
+            params.sort_order.cursor_query_params(params.direction);
+        let (cursor_clause, order, should_reverse) = if params.cursor.is_some() {
+            (
+                format!("AND (created_at, id) {} (?, ?)", comparison),
+                order,
+                should_reverse,
+            )
+        } else {
+            (String::new(), params.sort_order.as_sql(), false)
+        };
+
+        let sql = format!(
+            r#"
+            SELECT {COLUMNS}
+            FROM role_elevations
+            WHERE org_id = ? {cursor_clause}
+            ORDER BY created_at {order}, id {order}
+            LIMIT ?
+            "#
+        );
+
+        let mut q = query(&sql).bind(org_id.to_string());
+        if let Some(ref cursor) = params.cursor {
+            q = q.bind(cursor.created_at).bind(cursor.id.to_string());
+        }
+        let rows = q.bind(fetch_limit).fetch_all(&self.pool).await?;
+
+        let has_more = rows.len() as i64 > limit;
+        let mut items = rows
+            .iter()
+            .take(limit as usize)
+            .map(Self::parse_elevation)
+            .collect::<DbResult<Vec<_>>>()?;
+
+        if should_reverse {
+            items.reverse();
+        }
+
+        let direction = if params.cursor.is_some() {
+            params.direction
+        } else {
+            CursorDirection::Forward
+        };
+        let cursors =
+            PageCursors::from_items(&items, has_more, direction, params.cursor.as_ref(), |e| {
+                cursor_from_row(e.created_at, e.id)
+            });
+
+        Ok(ListResult::new(items, has_more, cursors))
+    }
+
+    async fn approve(
+        &self,
+        id: Uuid,
+        decided_by: Option<Uuid>,
+        note: Option<&str>,
+        previous_role: &str,
+        expires_at: DateTime<Utc>,
+    ) -> DbResult<RoleElevation> {
+        let now = truncate_to_millis(Utc::now());
+        let result = query(
+            r#"
+            UPDATE role_elevations
+            SET status = 'active', decided_by = ?, decision_note = ?, decided_at = ?,
+                previous_role = ?, expires_at = ?, updated_at = ?
+            WHERE id = ? AND status = 'pending'
+            "#,
+        )
+        .bind(decided_by.map(|id| id.to_string()))
+        .bind(note)
+        .bind(now)
+        .bind(previous_role)
+        .bind(truncate_to_millis(expires_at))
+        .bind(now)
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+        self.get_by_id(id).await?.ok_or(DbError::NotFound)
+    }
+
+    async fn deny(
+        &self,
+        id: Uuid,
+        decided_by: Option<Uuid>,
+        note: Option<&str>,
+    ) -> DbResult<RoleElevation> {
+        let now = truncate_to_millis(Utc::now());
+        let result = query(
+            r#"
+            UPDATE role_elevations
+            SET status = 'denied', decided_by = ?, decision_note = ?, decided_at = ?,
+                updated_at = ?
+            WHERE id = ? AND status = 'pending'
+            "#,
+        )
+        .bind(decided_by.map(|id| id.to_string()))
+        .bind(note)
+        .bind(now)
+        .bind(now)
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+        self.get_by_id(id).await?.ok_or(DbError::NotFound)
+    }
+
+    async fn end(
+        &self,
+        id: Uuid,
+        from: ElevationStatus,
+        to: ElevationStatus,
+        ended_by: Option<Uuid>,
+    ) -> DbResult<bool> {
+        let now = truncate_to_millis(Utc::now());
+        let result = query(
+            r#"
+            UPDATE role_elevations
+            SET status = ?, ended_at = ?, ended_by = ?, updated_at = ?
+            WHERE id = ? AND status = ?
+            "#,
+        )
+        .bind(to.as_str())
+        .bind(now)
+        .bind(ended_by.map(|id| id.to_string()))
+        .bind(now)
+        .bind(id.to_string())
+        .bind(from.as_str())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list_expired(&self, now: DateTime<Utc>, limit: i64) -> DbResult<Vec<RoleElevation>> {
+        let sql = format!(
+            "SELECT {COLUMNS} FROM role_elevations \
+             WHERE status = 'active' AND expires_at <= ? \
+             ORDER BY expires_at ASC, id ASC \
+             LIMIT ?"
+        );
+        let rows = query(&sql)
+            .bind(now)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter().map(Self::parse_elevation).collect()
+    }
+}
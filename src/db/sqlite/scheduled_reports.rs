@@ -0,0 +1,223 @@
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use uuid::Uuid;
+
+use super::{
+    backend::{Pool, Row, RowExt, query},
+    common::parse_uuid,
+};
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{
+            CursorDirection, ListParams, ListResult, PageCursors, ReportRunRepo, cursor_from_row,
+            truncate_to_millis,
+        },
+    },
+    models::{CreateReportRun, ReportRun, ReportRunFilter},
+};
+
+/// Columns selected by list queries. `content` is omitted because rendered
+/// reports can be large and listings never show them.
+const SUMMARY_COLUMNS: &str = "id, report_name, kind, org_id, period_start, period_end, \
+                               format, delivery, status, error, created_at";
+
+pub struct SqliteReportRunRepo {
+    pool: Pool,
+}
+
+impl SqliteReportRunRepo {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    fn parse_run(row: &Row, with_content: bool) -> DbResult<ReportRun> {
+        Ok(ReportRun {
+            id: parse_uuid(&row.col::<String>("id"))?,
+            report_name: row.col("report_name"),
+            kind: row
+                .col::<String>("kind")
+                .parse()
+                .map_err(DbError::Internal)?,
+            org_id: parse_uuid(&row.col::<String>("org_id"))?,
+            period_start: row.col("period_start"),
+            period_end: row.col("period_end"),
+            format: row
+                .col::<String>("format")
+                .parse()
+                .map_err(DbError::Internal)?,
+            delivery: row.col("delivery"),
+            status: row
+                .col::<String>("status")
+                .parse()
+                .map_err(DbError::Internal)?,
+            error: row.col("error"),
+            content: if with_content {
+                row.col("content")
+            } else {
+                None
+            },
+            created_at: row.col("created_at"),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl ReportRunRepo for SqliteReportRunRepo {
+    async fn create(&self, input: CreateReportRun) -> DbResult<ReportRun> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+
+        query(
+            r#"
+            INSERT INTO scheduled_report_runs (
+                id, report_name, kind, org_id, period_start, period_end,
+                format, delivery, status, error, content, created_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(id.to_string())
+        .bind(&input.report_name)
+        .bind(input.kind.as_str())
+        .bind(input.org_id.to_string())
+        .bind(input.period_start)
+        .bind(input.period_end)
+        .bind(input.format.as_str())
+        .bind(&input.delivery)
+        .bind(input.status.as_str())
+        .bind(&input.error)
+        .bind(&input.content)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(ReportRun {
+            id,
+            report_name: input.report_name,
+            kind: input.kind,
+            org_id: input.org_id,
+            period_start: input.period_start,
+            period_end: input.period_end,
+            format: input.format,
+            delivery: input.delivery,
+            status: input.status,
+            error: input.error,
+            content: input.content,
+            created_at: now,
+        })
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<ReportRun>> {
+        let sql =
+            format!("SELECT {SUMMARY_COLUMNS}, content FROM scheduled_report_runs WHERE id = ?");
+        let row = query(&sql)
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|r| Self::parse_run(&r, true)).transpose()
+    }
+
+    async fn list(
+        &self,
+        filter: ReportRunFilter,
+        params: ListParams,
+    ) -> DbResult<ListResult<ReportRun>> {
+        let limit = params.limit.unwrap_or(100);
+        let fetch_limit = limit + 1;
+
+        let mut conditions = Vec::new();
+        if filter.org_id.is_some() {
+            conditions.push("org_id = ?".to_string());
+        }
+        if filter.report_name.is_some() {
+            conditions.push("report_name = ?".to_string());
+        }
+
+        let (comparison, order, should_reverse) =
+            params.sort_order.cursor_query_params(params.direction);
+        let (order, should_reverse) = if params.cursor.is_some() {
+            conditions.push(format!("(created_at, id) {} (?, ?)", comparison));
+            (order, should_reverse)
+        } else {
+            (params.sort_order.as_sql(), false)
+        };
+
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+
+        let sql = format!(
+            r#"
+            SELECT {SUMMARY_COLUMNS}
+            FROM scheduled_report_runs
+            {where_clause}
+            ORDER BY created_at {order}, id {order}
+            LIMIT ?
+            "#
+        );
+
+        let mut q = query(&sql);
+        if let Some(org_id) = filter.org_id {
+            q = q.bind(org_id.to_string());
+        }
+        if let Some(ref report_name) = filter.report_name {
+            q = q.bind(report_name.clone());
+        }
+        if let Some(ref cursor) = params.cursor {
+            q = q.bind(cursor.created_at).bind(cursor.id.to_string());
+        }
+        let rows = q.bind(fetch_limit).fetch_all(&self.pool).await?;
+
+        let has_more = rows.len() as i64 > limit;
+        let mut items = rows
+            .iter()
+            .take(limit as usize)
+            .map(|row| Self::parse_run(row, false))
+            .collect::<DbResult<Vec<_>>>()?;
+
+        if should_reverse {
+            items.reverse();
+        }
+
+        let direction = if params.cursor.is_some() {
+            params.direction
+        } else {
+            CursorDirection::Forward
+        };
+        let cursors =
+            PageCursors::from_items(&items, has_more, direction, params.cursor.as_ref(), |run| {
+                cursor_from_row(run.created_at, run.id)
+            });
+
+        Ok(ListResult::new(items, has_more, cursors))
+    }
+
+    async fn list_for_period(
+        &self,
+        report_name: &str,
+        org_id: Uuid,
+        period_start: NaiveDate,
+    ) -> DbResult<Vec<ReportRun>> {
+        let sql = format!(
+            r#"
+            SELECT {SUMMARY_COLUMNS}
+            FROM scheduled_report_runs
+            WHERE report_name = ? AND org_id = ? AND period_start = ?
+            ORDER BY created_at ASC, id ASC
+            "#
+        );
+        let rows = query(&sql)
+            .bind(report_name)
+            .bind(org_id.to_string())
+            .bind(period_start)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter().map(|row| Self::parse_run(row, false)).collect()
+    }
+}
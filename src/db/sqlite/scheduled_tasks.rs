@@ -0,0 +1,389 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::{
+    backend::{Pool, Row, RowExt, begin, map_unique_violation, query},
+    common::parse_uuid,
+};
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{ScheduledTaskRepo, truncate_to_millis},
+    },
+    models::{
+        CreateScheduledTask, CreateScheduledTaskRun, ScheduledTask, ScheduledTaskDelivery,
+        ScheduledTaskProgress, ScheduledTaskRun, UpdateScheduledTask,
+    },
+};
+
+const TASK_COLUMNS: &str = "id, org_id, name, description, schedule, model, instructions, \
+                            prompt, delivery_type, conversation_id, webhook_url, \
+                            webhook_secret, enabled, next_run_at, last_run_at, \
+                            consecutive_failures, created_at, updated_at";
+
+const RUN_COLUMNS: &str = "id, task_id, status, output_text, error, input_tokens, \
+                           output_tokens, cost_microcents, started_at, finished_at";
+
+pub struct SqliteScheduledTaskRepo {
+    pool: Pool,
+}
+
+impl SqliteScheduledTaskRepo {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    fn parse_task(row: &Row) -> DbResult<ScheduledTask> {
+        let delivery_type: String = row.col("delivery_type");
+        let delivery = match delivery_type.as_str() {
+            "conversation" => ScheduledTaskDelivery::Conversation {
+                conversation_id: parse_uuid(
+                    &row.col::<Option<String>>("conversation_id")
+                        .unwrap_or_default(),
+                )?,
+            },
+            "webhook" => ScheduledTaskDelivery::Webhook {
+                url: row.col::<Option<String>>("webhook_url").unwrap_or_default(),
+                signing_secret: row.col("webhook_secret"),
+            },
+            other => {
+                return Err(DbError::Internal(format!(
+                    "Invalid scheduled task delivery type: {other}"
+                )));
+            }
+        };
+        let enabled: i32 = row.col("enabled");
+
+        Ok(ScheduledTask {
+            id: parse_uuid(&row.col::<String>("id"))?,
+            org_id: parse_uuid(&row.col::<String>("org_id"))?,
+            name: row.col("name"),
+            description: row.col("description"),
+            schedule: row.col("schedule"),
+            model: row.col("model"),
+            instructions: row.col("instructions"),
+            prompt: row.col("prompt"),
+            delivery,
+            enabled: enabled != 0,
+            next_run_at: row.col("next_run_at"),
+            last_run_at: row.col("last_run_at"),
+            consecutive_failures: row.col("consecutive_failures"),
+            created_at: row.col("created_at"),
+            updated_at: row.col("updated_at"),
+        })
+    }
+
+    fn parse_run(row: &Row) -> DbResult<ScheduledTaskRun> {
+        let status: String = row.col("status");
+        Ok(ScheduledTaskRun {
+            id: parse_uuid(&row.col::<String>("id"))?,
+            task_id: parse_uuid(&row.col::<String>("task_id"))?,
+            status: status.parse().map_err(DbError::Internal)?,
+            output_text: row.col("output_text"),
+            error: row.col("error"),
+            input_tokens: row.col("input_tokens"),
+            output_tokens: row.col("output_tokens"),
+            cost_microcents: row.col("cost_microcents"),
+            started_at: row.col("started_at"),
+            finished_at: row.col("finished_at"),
+        })
+    }
+}
+
+/// Split a delivery into its `conversation_id`, `webhook_url` and
+/// `webhook_secret` columns.
+fn delivery_columns(
+    delivery: &ScheduledTaskDelivery,
+) -> (Option<String>, Option<String>, Option<String>) {
+    match delivery {
+        ScheduledTaskDelivery::Conversation { conversation_id } => {
+            (Some(conversation_id.to_string()), None, None)
+        }
+        ScheduledTaskDelivery::Webhook {
+            url,
+            signing_secret,
+        } => (None, Some(url.clone()), signing_secret.clone()),
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl ScheduledTaskRepo for SqliteScheduledTaskRepo {
+    async fn create(
+        &self,
+        org_id: Uuid,
+        input: CreateScheduledTask,
+        next_run_at: Option<DateTime<Utc>>,
+    ) -> DbResult<ScheduledTask> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+        let next_run_at = next_run_at.map(truncate_to_millis);
+        let (conversation_id, webhook_url, webhook_secret) = delivery_columns(&input.delivery);
+
+        query(
+            r#"
+            INSERT INTO scheduled_tasks (
+                id, org_id, name, description, schedule, model, instructions, prompt,
+                delivery_type, conversation_id, webhook_url, webhook_secret, enabled,
+                next_run_at, consecutive_failures, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 0, ?, ?)
+            "#,
+        )
+        .bind(id.to_string())
+        .bind(org_id.to_string())
+        .bind(&input.name)
+        .bind(&input.description)
+        .bind(&input.schedule)
+        .bind(&input.model)
+        .bind(&input.instructions)
+        .bind(&input.prompt)
+        .bind(input.delivery.as_str())
+        .bind(&conversation_id)
+        .bind(&webhook_url)
+        .bind(&webhook_secret)
+        .bind(input.enabled as i32)
+        .bind(next_run_at)
+        .bind(now)
+        .bind(now)
+        .execute(&self.pool)
+        .await
+        .map_err(map_unique_violation(format!(
+            "Scheduled task '{}' already exists in this organization",
+            input.name
+        )))?;
+
+        Ok(ScheduledTask {
+            id,
+            org_id,
+            name: input.name,
+            description: input.description,
+            schedule: input.schedule,
+            model: input.model,
+            instructions: input.instructions,
+            prompt: input.prompt,
+            delivery: input.delivery,
+            enabled: input.enabled,
+            next_run_at,
+            last_run_at: None,
+            consecutive_failures: 0,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn get(&self, id: Uuid) -> DbResult<Option<ScheduledTask>> {
+        let sql = format!("SELECT {TASK_COLUMNS} FROM scheduled_tasks WHERE id = ?");
+        let row = query(&sql)
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|r| Self::parse_task(&r)).transpose()
+    }
+
+    async fn list_by_org(&self, org_id: Uuid) -> DbResult<Vec<ScheduledTask>> {
+        let sql = format!(
+            "SELECT {TASK_COLUMNS} FROM scheduled_tasks WHERE org_id = ? ORDER BY name ASC"
+        );
+        let rows = query(&sql)
+            .bind(org_id.to_string())
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter().map(Self::parse_task).collect()
+    }
+
+    async fn count_by_org(&self, org_id: Uuid) -> DbResult<i64> {
+        let row = query("SELECT COUNT(*) as count FROM scheduled_tasks WHERE org_id = ?")
+            .bind(org_id.to_string())
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(row.col("count"))
+    }
+
+    async fn update(
+        &self,
+        id: Uuid,
+        input: UpdateScheduledTask,
+        next_run_at: Option<DateTime<Utc>>,
+    ) -> DbResult<ScheduledTask> {
+        let mut task = self.get(id).await?.ok_or(DbError::NotFound)?;
+        input.apply(&mut task);
+        task.next_run_at = next_run_at.map(truncate_to_millis);
+        task.updated_at = truncate_to_millis(Utc::now());
+        let (conversation_id, webhook_url, webhook_secret) = delivery_columns(&task.delivery);
+
+        let result = query(
+            r#"
+            UPDATE scheduled_tasks SET name = ?, description = ?, schedule = ?, model = ?,
+                instructions = ?, prompt = ?, delivery_type = ?, conversation_id = ?,
+                webhook_url = ?, webhook_secret = ?, enabled = ?, next_run_at = ?,
+                consecutive_failures = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(&task.name)
+        .bind(&task.description)
+        .bind(&task.schedule)
+        .bind(&task.model)
+        .bind(&task.instructions)
+        .bind(&task.prompt)
+        .bind(task.delivery.as_str())
+        .bind(&conversation_id)
+        .bind(&webhook_url)
+        .bind(&webhook_secret)
+        .bind(task.enabled as i32)
+        .bind(task.next_run_at)
+        .bind(task.consecutive_failures)
+        .bind(task.updated_at)
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await
+        .map_err(map_unique_violation(format!(
+            "Scheduled task '{}' already exists in this organization",
+            task.name
+        )))?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+        Ok(task)
+    }
+
+    async fn delete(&self, id: Uuid) -> DbResult<()> {
+        // Runs are deleted explicitly because the test harness pool doesn't
+        // enable `PRAGMA foreign_keys`, so the `ON DELETE CASCADE` can't be
+        // relied on.
+        let mut tx = begin(&self.pool).await?;
+        query("DELETE FROM scheduled_task_runs WHERE task_id = ?")
+            .bind(id.to_string())
+            .execute(&mut *tx)
+            .await?;
+        let result = query("DELETE FROM scheduled_tasks WHERE id = ?")
+            .bind(id.to_string())
+            .execute(&mut *tx)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn list_due(&self, now: DateTime<Utc>, limit: i64) -> DbResult<Vec<ScheduledTask>> {
+        let sql = format!(
+            "SELECT {TASK_COLUMNS} FROM scheduled_tasks \
+             WHERE enabled = 1 AND next_run_at IS NOT NULL AND next_run_at <= ? \
+             ORDER BY next_run_at ASC, id ASC LIMIT ?"
+        );
+        let rows = query(&sql)
+            .bind(now)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter().map(Self::parse_task).collect()
+    }
+
+    async fn record_run(
+        &self,
+        run: CreateScheduledTaskRun,
+        progress: ScheduledTaskProgress,
+    ) -> DbResult<ScheduledTaskRun> {
+        let started_at = truncate_to_millis(run.started_at);
+        let finished_at = truncate_to_millis(run.finished_at);
+
+        let mut tx = begin(&self.pool).await?;
+        let result = query(
+            r#"
+            UPDATE scheduled_tasks SET next_run_at = ?, last_run_at = ?,
+                consecutive_failures = ?, enabled = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(progress.next_run_at.map(truncate_to_millis))
+        .bind(finished_at)
+        .bind(progress.consecutive_failures)
+        .bind(progress.enabled as i32)
+        .bind(run.task_id.to_string())
+        .execute(&mut *tx)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        query(
+            r#"
+            INSERT INTO scheduled_task_runs (
+                id, task_id, status, output_text, error, input_tokens, output_tokens,
+                cost_microcents, started_at, finished_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(run.id.to_string())
+        .bind(run.task_id.to_string())
+        .bind(run.status.as_str())
+        .bind(&run.output_text)
+        .bind(&run.error)
+        .bind(run.input_tokens)
+        .bind(run.output_tokens)
+        .bind(run.cost_microcents)
+        .bind(started_at)
+        .bind(finished_at)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(ScheduledTaskRun {
+            id: run.id,
+            task_id: run.task_id,
+            status: run.status,
+            output_text: run.output_text,
+            error: run.error,
+            input_tokens: run.input_tokens,
+            output_tokens: run.output_tokens,
+            cost_microcents: run.cost_microcents,
+            started_at,
+            finished_at,
+        })
+    }
+
+    async fn list_runs(&self, task_id: Uuid, limit: i64) -> DbResult<Vec<ScheduledTaskRun>> {
+        let sql = format!(
+            "SELECT {RUN_COLUMNS} FROM scheduled_task_runs WHERE task_id = ? \
+             ORDER BY started_at DESC, id DESC LIMIT ?"
+        );
+        let rows = query(&sql)
+            .bind(task_id.to_string())
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter().map(Self::parse_run).collect()
+    }
+
+    async fn prune_runs(&self, task_id: Uuid, keep: i64) -> DbResult<u64> {
+        let result = query(
+            r#"
+            DELETE FROM scheduled_task_runs
+            WHERE task_id = ? AND id NOT IN (
+                SELECT id FROM scheduled_task_runs
+                WHERE task_id = ?
+                ORDER BY started_at DESC, id DESC
+                LIMIT ?
+            )
+            "#,
+        )
+        .bind(task_id.to_string())
+        .bind(task_id.to_string())
+        .bind(keep)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
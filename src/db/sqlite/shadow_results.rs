@@ -0,0 +1,298 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use super::{
+    backend::{Pool, Row, RowExt, query},
+    common::parse_uuid,
+};
+use crate::{
+    db::{
+        error::DbResult,
+        repos::{
+            CursorDirection, ListParams, ListResult, PageCursors, ShadowResultRepo,
+            cursor_from_row, truncate_to_millis,
+        },
+    },
+    models::{CreateShadowResult, ShadowReportRow, ShadowResult, ShadowResultFilter},
+};
+
+const COLUMNS: &str = "id, rule, org_id, project_id, primary_provider, primary_model, \
+                       primary_status, primary_latency_ms, primary_input_tokens, \
+                       primary_output_tokens, primary_cost_microcents, primary_output, \
+                       shadow_provider, shadow_model, shadow_status, shadow_latency_ms, \
+                       shadow_input_tokens, shadow_output_tokens, shadow_cost_microcents, \
+                       shadow_output, shadow_error, similarity, created_at";
+
+/// Filter on `ShadowResultFilter`; each condition takes its parameter twice.
+const FILTER: &str = "(? IS NULL OR rule = ?) AND (? IS NULL OR org_id = ?) \
+                      AND (? IS NULL OR created_at >= ?) AND (? IS NULL OR created_at < ?)";
+
+pub struct SqliteShadowResultRepo {
+    pool: Pool,
+}
+
+impl SqliteShadowResultRepo {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    fn parse_result(row: &Row) -> DbResult<ShadowResult> {
+        let optional_uuid = |name: &str| {
+            row.col::<Option<String>>(name)
+                .map(|s| parse_uuid(&s))
+                .transpose()
+        };
+        Ok(ShadowResult {
+            id: parse_uuid(&row.col::<String>("id"))?,
+            rule: row.col("rule"),
+            org_id: optional_uuid("org_id")?,
+            project_id: optional_uuid("project_id")?,
+            primary_provider: row.col("primary_provider"),
+            primary_model: row.col("primary_model"),
+            primary_status: row.col("primary_status"),
+            primary_latency_ms: row.col("primary_latency_ms"),
+            primary_input_tokens: row.col("primary_input_tokens"),
+            primary_output_tokens: row.col("primary_output_tokens"),
+            primary_cost_microcents: row.col("primary_cost_microcents"),
+            primary_output: row.col("primary_output"),
+            shadow_provider: row.col("shadow_provider"),
+            shadow_model: row.col("shadow_model"),
+            shadow_status: row.col("shadow_status"),
+            shadow_latency_ms: row.col("shadow_latency_ms"),
+            shadow_input_tokens: row.col("shadow_input_tokens"),
+            shadow_output_tokens: row.col("shadow_output_tokens"),
+            shadow_cost_microcents: row.col("shadow_cost_microcents"),
+            shadow_output: row.col("shadow_output"),
+            shadow_error: row.col("shadow_error"),
+            similarity: row.col("similarity"),
+            created_at: row.col("created_at"),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl ShadowResultRepo for SqliteShadowResultRepo {
+    async fn create(&self, input: CreateShadowResult) -> DbResult<ShadowResult> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+
+        let sql = format!(
+            "INSERT INTO shadow_results ({COLUMNS}) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+        );
+        query(&sql)
+            .bind(id.to_string())
+            .bind(&input.rule)
+            .bind(&input.org_id.map(|id| id.to_string()))
+            .bind(&input.project_id.map(|id| id.to_string()))
+            .bind(&input.primary_provider)
+            .bind(&input.primary_model)
+            .bind(input.primary_status)
+            .bind(input.primary_latency_ms)
+            .bind(&input.primary_input_tokens)
+            .bind(&input.primary_output_tokens)
+            .bind(&input.primary_cost_microcents)
+            .bind(&input.primary_output)
+            .bind(&input.shadow_provider)
+            .bind(&input.shadow_model)
+            .bind(&input.shadow_status)
+            .bind(&input.shadow_latency_ms)
+            .bind(&input.shadow_input_tokens)
+            .bind(&input.shadow_output_tokens)
+            .bind(&input.shadow_cost_microcents)
+            .bind(&input.shadow_output)
+            .bind(&input.shadow_error)
+            .bind(&input.similarity)
+            .bind(now)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(ShadowResult {
+            id,
+            rule: input.rule,
+            org_id: input.org_id,
+            project_id: input.project_id,
+            primary_provider: input.primary_provider,
+            primary_model: input.primary_model,
+            primary_status: input.primary_status,
+            primary_latency_ms: input.primary_latency_ms,
+            primary_input_tokens: input.primary_input_tokens,
+            primary_output_tokens: input.primary_output_tokens,
+            primary_cost_microcents: input.primary_cost_microcents,
+            primary_output: input.primary_output,
+            shadow_provider: input.shadow_provider,
+            shadow_model: input.shadow_model,
+            shadow_status: input.shadow_status,
+            shadow_latency_ms: input.shadow_latency_ms,
+            shadow_input_tokens: input.shadow_input_tokens,
+            shadow_output_tokens: input.shadow_output_tokens,
+            shadow_cost_microcents: input.shadow_cost_microcents,
+            shadow_output: input.shadow_output,
+            shadow_error: input.shadow_error,
+            similarity: input.similarity,
+            created_at: now,
+        })
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<ShadowResult>> {
+        let sql = format!("SELECT {COLUMNS} FROM shadow_results WHERE id = ?");
+        let row = query(&sql)
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|r| Self::parse_result(&r)).transpose()
+    }
+
+    async fn list(
+        &self,
+        filter: &ShadowResultFilter,
+        params: ListParams,
+    ) -> DbResult<ListResult<ShadowResult>> {
+        let limit = params.limit.unwrap_or(100);
+        let fetch_limit = limit + 1;
+
+        let (comparison, order, should_reverse) =
+            params.sort_order.cursor_query_params(params.direction);
+        let (cursor_clause, order, should_reverse) = if params.cursor.is_some() {
+            (
+                format!("AND (created_at, id) {} (?, ?)", comparison),
+                order,
+                should_reverse,
+            )
+        } else {
+            (String::new(), params.sort_order.as_sql(), false)
+        };
+
+        let sql = format!(
+            r#"
+            SELECT {COLUMNS}
+            FROM shadow_results
+            WHERE {FILTER} {cursor_clause}
+            ORDER BY created_at {order}, id {order}
+            LIMIT ?
+            "#
+        );
+
+        let org_id = filter.org_id.map(|id| id.to_string());
+        let mut q = query(&sql)
+            .bind(&filter.rule)
+            .bind(&filter.rule)
+            .bind(&org_id)
+            .bind(&org_id)
+            .bind(&filter.from)
+            .bind(&filter.from)
+            .bind(&filter.to)
+            .bind(&filter.to);
+        if let Some(ref cursor) = params.cursor {
+            q = q.bind(cursor.created_at).bind(cursor.id.to_string());
+        }
+        let rows = q.bind(fetch_limit).fetch_all(&self.pool).await?;
+
+        let has_more = rows.len() as i64 > limit;
+        let mut items = rows
+            .iter()
+            .take(limit as usize)
+            .map(Self::parse_result)
+            .collect::<DbResult<Vec<_>>>()?;
+
+        if should_reverse {
+            items.reverse();
+        }
+
+        let direction = if params.cursor.is_some() {
+            params.direction
+        } else {
+            CursorDirection::Forward
+        };
+        let cursors = PageCursors::from_items(
+            &items,
+            has_more,
+            direction,
+            params.cursor.as_ref(),
+            |result| cursor_from_row(result.created_at, result.id),
+        );
+
+        Ok(ListResult::new(items, has_more, cursors))
+    }
+
+    async fn report(&self, filter: &ShadowResultFilter) -> DbResult<Vec<ShadowReportRow>> {
+        let sql = format!(
+            r#"
+            SELECT rule, primary_provider, primary_model, shadow_provider, shadow_model,
+                   COUNT(*) AS samples,
+                   SUM(CASE WHEN primary_status BETWEEN 200 AND 299 THEN 0 ELSE 1 END)
+                       AS primary_errors,
+                   SUM(CASE WHEN shadow_status BETWEEN 200 AND 299 THEN 0 ELSE 1 END)
+                       AS shadow_errors,
+                   SUM(CASE WHEN similarity >= 1.0 THEN 1 ELSE 0 END) AS identical,
+                   AVG(similarity) AS avg_similarity,
+                   AVG(CAST(primary_latency_ms AS REAL)) AS primary_avg_latency_ms,
+                   AVG(CAST(shadow_latency_ms AS REAL)) AS shadow_avg_latency_ms,
+                   COALESCE(SUM(primary_output_tokens), 0) AS primary_output_tokens,
+                   COALESCE(SUM(shadow_output_tokens), 0) AS shadow_output_tokens,
+                   COALESCE(SUM(primary_cost_microcents), 0) AS primary_cost_microcents,
+                   COALESCE(SUM(shadow_cost_microcents), 0) AS shadow_cost_microcents
+            FROM shadow_results
+            WHERE {FILTER}
+            GROUP BY rule, primary_provider, primary_model, shadow_provider, shadow_model
+            ORDER BY rule, samples DESC
+            "#
+        );
+
+        let org_id = filter.org_id.map(|id| id.to_string());
+        let rows = query(&sql)
+            .bind(&filter.rule)
+            .bind(&filter.rule)
+            .bind(&org_id)
+            .bind(&org_id)
+            .bind(&filter.from)
+            .bind(&filter.from)
+            .bind(&filter.to)
+            .bind(&filter.to)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| ShadowReportRow {
+                rule: row.col("rule"),
+                primary_provider: row.col("primary_provider"),
+                primary_model: row.col("primary_model"),
+                shadow_provider: row.col("shadow_provider"),
+                shadow_model: row.col("shadow_model"),
+                samples: row.col("samples"),
+                primary_errors: row.col("primary_errors"),
+                shadow_errors: row.col("shadow_errors"),
+                identical: row.col("identical"),
+                avg_similarity: row.col("avg_similarity"),
+                primary_avg_latency_ms: row.col("primary_avg_latency_ms"),
+                shadow_avg_latency_ms: row.col("shadow_avg_latency_ms"),
+                primary_output_tokens: row.col("primary_output_tokens"),
+                shadow_output_tokens: row.col("shadow_output_tokens"),
+                primary_cost_microcents: row.col("primary_cost_microcents"),
+                shadow_cost_microcents: row.col("shadow_cost_microcents"),
+            })
+            .collect())
+    }
+
+    async fn delete(&self, filter: &ShadowResultFilter) -> DbResult<u64> {
+        let sql = format!("DELETE FROM shadow_results WHERE {FILTER}");
+        let org_id = filter.org_id.map(|id| id.to_string());
+        let result = query(&sql)
+            .bind(&filter.rule)
+            .bind(&filter.rule)
+            .bind(&org_id)
+            .bind(&org_id)
+            .bind(&filter.from)
+            .bind(&filter.from)
+            .bind(&filter.to)
+            .bind(&filter.to)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
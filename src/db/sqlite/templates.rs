@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use async_trait::async_trait;
 use uuid::Uuid;
@@ -48,6 +48,8 @@ impl SqliteTemplateRepo {
             description: row.col("description"),
             content: row.col("content"),
             metadata,
+            labels: serde_json::from_str(&row.col::<String>("labels"))
+                .map_err(|e| DbError::Internal(format!("failed to deserialize labels: {e}")))?,
             created_at: row.col("created_at"),
             updated_at: row.col("updated_at"),
         })
@@ -93,10 +95,11 @@ impl SqliteTemplateRepo {
 
         let sql = format!(
             r#"
-            SELECT id, owner_type, owner_id, name, description, content, metadata, created_at, updated_at
+            SELECT id, owner_type, owner_id, name, description, content, metadata, labels, created_at, updated_at
             FROM templates
             WHERE owner_type = ? AND owner_id = ? AND (created_at, id) {} (?, ?)
             {}
+            AND NOT EXISTS (SELECT 1 FROM json_each(?) l WHERE json_extract(labels, '$."' || l.key || '"') IS NOT l.value)
             ORDER BY created_at {}, id {}
             LIMIT ?
             "#,
@@ -108,6 +111,7 @@ impl SqliteTemplateRepo {
             .bind(owner_id.to_string())
             .bind(cursor.created_at)
             .bind(cursor.id.to_string())
+            .bind(params.labels.to_json().to_string())
             .bind(fetch_limit)
             .fetch_all(&self.pool)
             .await?;
@@ -178,6 +182,7 @@ impl TemplateRepo for SqliteTemplateRepo {
             description: input.description,
             content: input.content,
             metadata: input.metadata,
+            labels: Default::default(),
             created_at: now,
             updated_at: now,
         })
@@ -186,7 +191,7 @@ impl TemplateRepo for SqliteTemplateRepo {
     async fn get_by_id(&self, id: Uuid) -> DbResult<Option<Template>> {
         let result = query(
             r#"
-            SELECT id, owner_type, owner_id, name, description, content, metadata, created_at, updated_at
+            SELECT id, owner_type, owner_id, name, description, content, metadata, labels, created_at, updated_at
             FROM templates
             WHERE id = ? AND deleted_at IS NULL
             "#,
@@ -204,7 +209,7 @@ impl TemplateRepo for SqliteTemplateRepo {
     async fn get_by_id_and_org(&self, id: Uuid, org_id: Uuid) -> DbResult<Option<Template>> {
         let result = query(
             r#"
-            SELECT p.id, p.owner_type, p.owner_id, p.name, p.description, p.content, p.metadata, p.created_at, p.updated_at
+            SELECT p.id, p.owner_type, p.owner_id, p.name, p.description, p.content, p.metadata, p.labels, p.created_at, p.updated_at
             FROM templates p
             WHERE p.id = ? AND p.deleted_at IS NULL
             AND (
@@ -255,17 +260,19 @@ impl TemplateRepo for SqliteTemplateRepo {
 
         let sql = if params.include_deleted {
             r#"
-            SELECT id, owner_type, owner_id, name, description, content, metadata, created_at, updated_at
+            SELECT id, owner_type, owner_id, name, description, content, metadata, labels, created_at, updated_at
             FROM templates
             WHERE owner_type = ? AND owner_id = ?
+            AND NOT EXISTS (SELECT 1 FROM json_each(?) l WHERE json_extract(labels, '$."' || l.key || '"') IS NOT l.value)
             ORDER BY created_at DESC, id DESC
             LIMIT ?
             "#
         } else {
             r#"
-            SELECT id, owner_type, owner_id, name, description, content, metadata, created_at, updated_at
+            SELECT id, owner_type, owner_id, name, description, content, metadata, labels, created_at, updated_at
             FROM templates
             WHERE owner_type = ? AND owner_id = ? AND deleted_at IS NULL
+            AND NOT EXISTS (SELECT 1 FROM json_each(?) l WHERE json_extract(labels, '$."' || l.key || '"') IS NOT l.value)
             ORDER BY created_at DESC, id DESC
             LIMIT ?
             "#
@@ -274,6 +281,7 @@ impl TemplateRepo for SqliteTemplateRepo {
         let rows = query(sql)
             .bind(owner_type.as_str())
             .bind(owner_id.to_string())
+            .bind(params.labels.to_json().to_string())
             .bind(fetch_limit)
             .fetch_all(&self.pool)
             .await?;
@@ -308,10 +316,11 @@ impl TemplateRepo for SqliteTemplateRepo {
 
             let sql = format!(
                 r#"
-                SELECT p.id, p.owner_type, p.owner_id, p.name, p.description, p.content, p.metadata, p.created_at, p.updated_at
+                SELECT p.id, p.owner_type, p.owner_id, p.name, p.description, p.content, p.metadata, p.labels, p.created_at, p.updated_at
                 FROM templates p
                 WHERE p.deleted_at IS NULL AND (p.created_at, p.id) {} (?, ?)
                 {}
+                AND NOT EXISTS (SELECT 1 FROM json_each(?) l WHERE json_extract(p.labels, '$."' || l.key || '"') IS NOT l.value)
                 ORDER BY p.created_at {}, p.id {}
                 LIMIT ?
                 "#,
@@ -328,6 +337,7 @@ impl TemplateRepo for SqliteTemplateRepo {
                 .bind(&org_str)
                 .bind(&org_str)
                 .bind(&org_str)
+                .bind(params.labels.to_json().to_string())
                 .bind(fetch_limit)
                 .fetch_all(&self.pool)
                 .await?;
@@ -353,10 +363,11 @@ impl TemplateRepo for SqliteTemplateRepo {
 
         let sql = format!(
             r#"
-            SELECT p.id, p.owner_type, p.owner_id, p.name, p.description, p.content, p.metadata, p.created_at, p.updated_at
+            SELECT p.id, p.owner_type, p.owner_id, p.name, p.description, p.content, p.metadata, p.labels, p.created_at, p.updated_at
             FROM templates p
             WHERE p.deleted_at IS NULL
             {}
+            AND NOT EXISTS (SELECT 1 FROM json_each(?) l WHERE json_extract(p.labels, '$."' || l.key || '"') IS NOT l.value)
             ORDER BY p.created_at DESC, p.id DESC
             LIMIT ?
             "#,
@@ -368,6 +379,7 @@ impl TemplateRepo for SqliteTemplateRepo {
             .bind(&org_str)
             .bind(&org_str)
             .bind(&org_str)
+            .bind(params.labels.to_json().to_string())
             .bind(fetch_limit)
             .fetch_all(&self.pool)
             .await?;
@@ -471,6 +483,31 @@ impl TemplateRepo for SqliteTemplateRepo {
         self.get_by_id(id).await?.ok_or(DbError::NotFound)
     }
 
+    async fn set_labels(&self, id: Uuid, labels: &BTreeMap<String, String>) -> DbResult<Template> {
+        let now = truncate_to_millis(chrono::Utc::now());
+        let labels = serde_json::to_string(labels)
+            .map_err(|e| DbError::Internal(format!("failed to serialize labels: {e}")))?;
+
+        let result = query(
+            r#"
+            UPDATE templates
+            SET labels = ?, updated_at = ?
+            WHERE id = ? AND deleted_at IS NULL
+            "#,
+        )
+        .bind(labels)
+        .bind(now)
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        self.get_by_id(id).await?.ok_or(DbError::NotFound)
+    }
+
     async fn delete(&self, id: Uuid) -> DbResult<()> {
         let now = truncate_to_millis(chrono::Utc::now());
 
@@ -518,6 +555,7 @@ mod tests {
                 description TEXT,
                 content TEXT NOT NULL,
                 metadata TEXT,
+                labels TEXT NOT NULL DEFAULT '{}',
                 created_at TEXT NOT NULL DEFAULT (datetime('now')),
                 updated_at TEXT NOT NULL DEFAULT (datetime('now')),
                 deleted_at TEXT,
@@ -16,9 +16,10 @@ use crate::{
     },
     models::{
         DailyModelSpend, DailyOrgSpend, DailyPricingSourceSpend, DailyProjectSpend,
-        DailyProviderSpend, DailySpend, DailyTeamSpend, DailyUserSpend, ModelSpend, OrgSpend,
-        PricingSourceSpend, ProjectSpend, ProviderSpend, RefererSpend, TeamSpend, UsageLogEntry,
-        UsageLogRecord, UsageSummary, UserSpend,
+        DailyProviderAttempts, DailyProviderSpend, DailyReliability, DailySpend, DailyTagSpend,
+        DailyTeamSpend, DailyUserSpend, HourlySpend, ModelSpend, OrgSpend, PricingSourceSpend,
+        ProjectSpend, ProviderSpend, RELIABILITY_LATENCY_BUCKETS_MS, RefererSpend, TagSpend,
+        TeamSpend, UsageLogEntry, UsageLogRecord, UsageMix, UsageSummary, UserSpend,
     },
 };
 
@@ -37,6 +38,12 @@ impl SqliteUsageRepo {
         Self { pool }
     }
 
+    /// JSON path to tag `key`. Tag keys are restricted to characters that
+    /// need no escaping inside the quotes.
+    fn tag_path(key: &str) -> String {
+        format!("$.\"{key}\"")
+    }
+
     fn media_fields(row: &super::backend::Row) -> (i64, i64, i64) {
         (
             row.col("image_count"),
@@ -65,9 +72,13 @@ impl UsageRepo for SqliteUsageRepo {
                 image_count, audio_seconds, character_count, provider_source,
                 record_type, tool_name, tool_query, tool_url,
                 tool_bytes_fetched, tool_results_count, tool_runtime_seconds,
-                tool_exit_code
+                tool_exit_code, jwt_subject, error_code,
+                structured_output_repairs, context_original_tokens, context_compressed_tokens,
+                degraded_from_model, degradation_reason, ttft_ms,
+                inter_token_latency_ms, output_tokens_per_second, idempotency_key,
+                provider_attempts, suspected_duplicate_attempts, tags
             )
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(id.to_string())
@@ -106,6 +117,20 @@ impl UsageRepo for SqliteUsageRepo {
         .bind(entry.tool_results_count)
         .bind(entry.tool_runtime_seconds)
         .bind(entry.tool_exit_code)
+        .bind(&entry.jwt_subject)
+        .bind(&entry.error_code)
+        .bind(entry.structured_output_repairs)
+        .bind(entry.context_original_tokens)
+        .bind(entry.context_compressed_tokens)
+        .bind(&entry.degraded_from_model)
+        .bind(&entry.degradation_reason)
+        .bind(entry.ttft_ms)
+        .bind(entry.inter_token_latency_ms)
+        .bind(entry.output_tokens_per_second)
+        .bind(&entry.idempotency_key)
+        .bind(entry.provider_attempts)
+        .bind(entry.suspected_duplicate_attempts)
+        .bind(entry.tags.as_ref().and_then(|t| serde_json::to_string(t).ok()))
         .execute(&self.pool)
         .await?;
 
@@ -118,8 +143,8 @@ impl UsageRepo for SqliteUsageRepo {
         }
 
         // SQLite has a limit of 999 parameters per query (SQLITE_LIMIT_VARIABLE_NUMBER)
-        // Each entry uses 36 parameters. Use 27 entries (36*27=972) to stay under limit.
-        const MAX_ENTRIES_PER_BATCH: usize = 27;
+        // Each entry uses 50 parameters. Use 19 entries (50*19=950) to stay within the limit.
+        const MAX_ENTRIES_PER_BATCH: usize = 19;
 
         let mut total_inserted = 0;
 
@@ -128,7 +153,7 @@ impl UsageRepo for SqliteUsageRepo {
         for chunk in entries.chunks(MAX_ENTRIES_PER_BATCH) {
             let placeholders: Vec<&str> = chunk
                 .iter()
-                .map(|_| "(?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)")
+                .map(|_| "(?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)")
                 .collect();
 
             let sql = format!(
@@ -142,7 +167,11 @@ impl UsageRepo for SqliteUsageRepo {
                     image_count, audio_seconds, character_count, provider_source,
                     record_type, tool_name, tool_query, tool_url,
                     tool_bytes_fetched, tool_results_count, tool_runtime_seconds,
-                    tool_exit_code
+                    tool_exit_code, jwt_subject, error_code,
+                    structured_output_repairs, context_original_tokens, context_compressed_tokens,
+                    degraded_from_model, degradation_reason, ttft_ms,
+                    inter_token_latency_ms, output_tokens_per_second, idempotency_key,
+                    provider_attempts, suspected_duplicate_attempts, tags
                 )
                 VALUES {}
                 "#,
@@ -191,7 +220,26 @@ impl UsageRepo for SqliteUsageRepo {
                     .bind(entry.tool_bytes_fetched)
                     .bind(entry.tool_results_count)
                     .bind(entry.tool_runtime_seconds)
-                    .bind(entry.tool_exit_code);
+                    .bind(entry.tool_exit_code)
+                    .bind(&entry.jwt_subject)
+                    .bind(&entry.error_code)
+                    .bind(entry.structured_output_repairs)
+                    .bind(entry.context_original_tokens)
+                    .bind(entry.context_compressed_tokens)
+                    .bind(&entry.degraded_from_model)
+                    .bind(&entry.degradation_reason)
+                    .bind(entry.ttft_ms)
+                    .bind(entry.inter_token_latency_ms)
+                    .bind(entry.output_tokens_per_second)
+                    .bind(&entry.idempotency_key)
+                    .bind(entry.provider_attempts)
+                    .bind(entry.suspected_duplicate_attempts)
+                    .bind(
+                        entry
+                            .tags
+                            .as_ref()
+                            .and_then(|t| serde_json::to_string(t).ok()),
+                    );
             }
 
             let result = query_builder.execute(&mut *tx).await?;
@@ -3898,6 +3946,315 @@ impl UsageRepo for SqliteUsageRepo {
             .collect())
     }
 
+    // ==================== Anomaly Detection Queries ====================
+
+    async fn get_hourly_spend(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> DbResult<Vec<HourlySpend>> {
+        let rows = query(
+            r#"
+            SELECT api_key_id, org_id,
+                strftime('%Y-%m-%dT%H:00:00Z', recorded_at) as hour,
+                COALESCE(SUM(cost_microcents), 0) as total_cost_microcents,
+                COUNT(*) as request_count
+            FROM usage_records
+            WHERE recorded_at >= ? AND recorded_at < ?
+            GROUP BY api_key_id, org_id, strftime('%Y-%m-%dT%H:00:00Z', recorded_at)
+            ORDER BY hour ASC
+            "#,
+        )
+        .bind(since)
+        .bind(until)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| HourlySpend {
+                api_key_id: row
+                    .col::<Option<String>>("api_key_id")
+                    .and_then(|s| s.parse().ok()),
+                org_id: row
+                    .col::<Option<String>>("org_id")
+                    .and_then(|s| s.parse().ok()),
+                hour: row.col("hour"),
+                total_cost_microcents: row.col("total_cost_microcents"),
+                request_count: row.col("request_count"),
+            })
+            .collect())
+    }
+
+    async fn get_usage_mix(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> DbResult<Vec<UsageMix>> {
+        let rows = query(
+            r#"
+            SELECT api_key_id, org_id, model, http_referer as referer,
+                COALESCE(SUM(cost_microcents), 0) as total_cost_microcents,
+                COUNT(*) as request_count
+            FROM usage_records
+            WHERE recorded_at >= ? AND recorded_at < ?
+                AND record_type = 'model'
+            GROUP BY api_key_id, org_id, model, http_referer
+            "#,
+        )
+        .bind(since)
+        .bind(until)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| UsageMix {
+                api_key_id: row
+                    .col::<Option<String>>("api_key_id")
+                    .and_then(|s| s.parse().ok()),
+                org_id: row
+                    .col::<Option<String>>("org_id")
+                    .and_then(|s| s.parse().ok()),
+                model: row.col("model"),
+                referer: row.col("referer"),
+                total_cost_microcents: row.col("total_cost_microcents"),
+                request_count: row.col("request_count"),
+            })
+            .collect())
+    }
+
+    // ==================== Reconciliation Queries ====================
+
+    async fn get_daily_provider_attempts(
+        &self,
+        range: DateRange,
+    ) -> DbResult<Vec<DailyProviderAttempts>> {
+        let rows = query(
+            r#"
+            SELECT
+                date(recorded_at) as date,
+                provider,
+                model,
+                COUNT(*) as request_count,
+                COALESCE(SUM(provider_attempts), 0) as provider_attempts,
+                COALESCE(SUM(suspected_duplicate_attempts), 0) as suspected_duplicate_attempts,
+                COALESCE(SUM(input_tokens), 0) as input_tokens,
+                COALESCE(SUM(output_tokens), 0) as output_tokens,
+                COALESCE(SUM(cost_microcents), 0) as cost_microcents
+            FROM usage_records
+            WHERE recorded_at >= ?
+                AND recorded_at < date(?, '+1 day')
+                AND record_type = 'model'
+            GROUP BY date(recorded_at), provider, model
+            ORDER BY date(recorded_at) ASC, provider ASC, model ASC
+            "#,
+        )
+        .bind(range.start)
+        .bind(range.end)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| DailyProviderAttempts {
+                date: row.col("date"),
+                provider: row.col("provider"),
+                model: row.col("model"),
+                request_count: row.col("request_count"),
+                provider_attempts: row.col("provider_attempts"),
+                suspected_duplicate_attempts: row.col("suspected_duplicate_attempts"),
+                input_tokens: row.col("input_tokens"),
+                output_tokens: row.col("output_tokens"),
+                cost_microcents: row.col("cost_microcents"),
+            })
+            .collect())
+    }
+
+    // ==================== Tag Queries ====================
+
+    async fn get_tag_usage(
+        &self,
+        key: &str,
+        range: DateRange,
+        org_id: Option<Uuid>,
+        project_id: Option<Uuid>,
+    ) -> DbResult<Vec<TagSpend>> {
+        let org_id = org_id.map(|id| id.to_string());
+        let project_id = project_id.map(|id| id.to_string());
+        let rows = query(
+            r#"
+            SELECT
+                json_extract(tags, ?) as tag_value,
+                COALESCE(SUM(cost_microcents), 0) as total_cost_microcents,
+                COALESCE(SUM(input_tokens), 0) as input_tokens,
+                COALESCE(SUM(output_tokens), 0) as output_tokens,
+                COALESCE(SUM(total_tokens), 0) as total_tokens,
+                COUNT(*) as request_count
+            FROM usage_records
+            WHERE recorded_at >= ?
+                AND recorded_at < date(?, '+1 day')
+                AND (? IS NULL OR org_id = ?)
+                AND (? IS NULL OR project_id = ?)
+            GROUP BY tag_value
+            ORDER BY total_cost_microcents DESC
+            "#,
+        )
+        .bind(Self::tag_path(key))
+        .bind(range.start)
+        .bind(range.end)
+        .bind(&org_id)
+        .bind(&org_id)
+        .bind(&project_id)
+        .bind(&project_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| TagSpend {
+                tag_value: row.col("tag_value"),
+                total_cost_microcents: row.col("total_cost_microcents"),
+                input_tokens: row.col("input_tokens"),
+                output_tokens: row.col("output_tokens"),
+                total_tokens: row.col("total_tokens"),
+                request_count: row.col("request_count"),
+            })
+            .collect())
+    }
+
+    async fn get_daily_tag_usage(
+        &self,
+        key: &str,
+        range: DateRange,
+        org_id: Option<Uuid>,
+        project_id: Option<Uuid>,
+    ) -> DbResult<Vec<DailyTagSpend>> {
+        let org_id = org_id.map(|id| id.to_string());
+        let project_id = project_id.map(|id| id.to_string());
+        let rows = query(
+            r#"
+            SELECT
+                date(recorded_at) as date,
+                json_extract(tags, ?) as tag_value,
+                COALESCE(SUM(cost_microcents), 0) as total_cost_microcents,
+                COALESCE(SUM(input_tokens), 0) as input_tokens,
+                COALESCE(SUM(output_tokens), 0) as output_tokens,
+                COALESCE(SUM(total_tokens), 0) as total_tokens,
+                COUNT(*) as request_count
+            FROM usage_records
+            WHERE recorded_at >= ?
+                AND recorded_at < date(?, '+1 day')
+                AND (? IS NULL OR org_id = ?)
+                AND (? IS NULL OR project_id = ?)
+            GROUP BY date(recorded_at), tag_value
+            ORDER BY date(recorded_at) ASC, total_cost_microcents DESC
+            "#,
+        )
+        .bind(Self::tag_path(key))
+        .bind(range.start)
+        .bind(range.end)
+        .bind(&org_id)
+        .bind(&org_id)
+        .bind(&project_id)
+        .bind(&project_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| DailyTagSpend {
+                date: row.col("date"),
+                tag_value: row.col("tag_value"),
+                total_cost_microcents: row.col("total_cost_microcents"),
+                input_tokens: row.col("input_tokens"),
+                output_tokens: row.col("output_tokens"),
+                total_tokens: row.col("total_tokens"),
+                request_count: row.col("request_count"),
+            })
+            .collect())
+    }
+
+    // ==================== Reliability Queries ====================
+
+    async fn get_daily_reliability(
+        &self,
+        range: DateRange,
+        org_id: Option<Uuid>,
+        project_id: Option<Uuid>,
+    ) -> DbResult<Vec<DailyReliability>> {
+        let latency_buckets = RELIABILITY_LATENCY_BUCKETS_MS
+            .iter()
+            .enumerate()
+            .map(|(i, bound)| {
+                format!(
+                    "COALESCE(SUM(CASE WHEN u.latency_ms <= {bound} THEN 1 ELSE 0 END), 0) as latency_le_{i}"
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",\n                ");
+        let org_id = org_id.map(|id| id.to_string());
+        let project_id = project_id.map(|id| id.to_string());
+        let rows = query(&format!(
+            r#"
+            SELECT
+                date(u.recorded_at) as date,
+                u.provider,
+                u.model,
+                u.project_id,
+                projects.name as project_name,
+                COUNT(*) as request_count,
+                COUNT(u.error_code) as error_count,
+                COALESCE(SUM(CASE WHEN u.error_code IN ('provider_error', 'provider_unavailable', 'timeout') THEN 1 ELSE 0 END), 0) as provider_errors,
+                COALESCE(SUM(CASE WHEN u.error_code = 'content_filtered' THEN 1 ELSE 0 END), 0) as guardrail_blocks,
+                COALESCE(SUM(CASE WHEN u.error_code = 'rate_limited' THEN 1 ELSE 0 END), 0) as rate_limited,
+                COALESCE(SUM(CASE WHEN u.cached_tokens > 0 THEN 1 ELSE 0 END), 0) as cache_hits,
+                COUNT(u.latency_ms) as latency_count,
+                {latency_buckets}
+            FROM usage_records u
+            LEFT JOIN projects ON u.project_id = projects.id
+            WHERE u.recorded_at >= ?
+                AND u.recorded_at < date(?, '+1 day')
+                AND u.record_type = 'model'
+                AND (? IS NULL OR u.org_id = ?)
+                AND (? IS NULL OR u.project_id = ?)
+            GROUP BY date(u.recorded_at), u.provider, u.model, u.project_id, projects.name
+            ORDER BY date(u.recorded_at) ASC, u.provider ASC, u.model ASC
+            "#,
+        ))
+        .bind(range.start)
+        .bind(range.end)
+        .bind(&org_id)
+        .bind(&org_id)
+        .bind(&project_id)
+        .bind(&project_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| DailyReliability {
+                date: row.col("date"),
+                provider: row.col("provider"),
+                model: row.col("model"),
+                project_id: row
+                    .col::<Option<String>>("project_id")
+                    .and_then(|s| s.parse().ok()),
+                project_name: row.col("project_name"),
+                request_count: row.col("request_count"),
+                error_count: row.col("error_count"),
+                provider_errors: row.col("provider_errors"),
+                guardrail_blocks: row.col("guardrail_blocks"),
+                rate_limited: row.col("rate_limited"),
+                cache_hits: row.col("cache_hits"),
+                latency_count: row.col("latency_count"),
+                latency_buckets: (0..RELIABILITY_LATENCY_BUCKETS_MS.len())
+                    .map(|i| row.col(&format!("latency_le_{i}")))
+                    .collect(),
+            })
+            .collect())
+    }
+
     // ==================== Individual Log Queries ====================
 
     async fn list_logs(&self, filter: UsageLogQuery) -> DbResult<ListResult<UsageLogRecord>> {
@@ -3959,6 +4316,10 @@ impl UsageRepo for SqliteUsageRepo {
             conditions.push("record_type = ?".to_string());
             params.push(record_type.clone());
         }
+        if let Some(ref error_code) = filter.error_code {
+            conditions.push("error_code = ?".to_string());
+            params.push(error_code.clone());
+        }
         if let Some(from) = &filter.from {
             conditions.push("recorded_at >= ?".to_string());
             params.push(from.to_rfc3339());
@@ -3999,7 +4360,11 @@ impl UsageRepo for SqliteUsageRepo {
                    image_count, audio_seconds, character_count, provider_source,
                    record_type, tool_name, tool_query, tool_url,
                    tool_bytes_fetched, tool_results_count, tool_runtime_seconds,
-                   tool_exit_code
+                   tool_exit_code, jwt_subject, error_code,
+                   structured_output_repairs, context_original_tokens, context_compressed_tokens,
+                   degraded_from_model, degradation_reason, ttft_ms,
+                   inter_token_latency_ms, output_tokens_per_second, idempotency_key,
+                   provider_attempts, suspected_duplicate_attempts, tags
             FROM usage_records
             {}
             ORDER BY recorded_at {}, id {}
@@ -4070,6 +4435,22 @@ impl UsageRepo for SqliteUsageRepo {
                     tool_results_count: row.col("tool_results_count"),
                     tool_runtime_seconds: row.col("tool_runtime_seconds"),
                     tool_exit_code: row.col("tool_exit_code"),
+                    jwt_subject: row.col("jwt_subject"),
+                    error_code: row.col("error_code"),
+                    structured_output_repairs: row.col("structured_output_repairs"),
+                    context_original_tokens: row.col("context_original_tokens"),
+                    context_compressed_tokens: row.col("context_compressed_tokens"),
+                    degraded_from_model: row.col("degraded_from_model"),
+                    degradation_reason: row.col("degradation_reason"),
+                    ttft_ms: row.col("ttft_ms"),
+                    inter_token_latency_ms: row.col("inter_token_latency_ms"),
+                    output_tokens_per_second: row.col("output_tokens_per_second"),
+                    idempotency_key: row.col("idempotency_key"),
+                    provider_attempts: row.col("provider_attempts"),
+                    suspected_duplicate_attempts: row.col("suspected_duplicate_attempts"),
+                    tags: row
+                        .col::<Option<String>>("tags")
+                        .and_then(|s| serde_json::from_str(&s).ok()),
                 })
             })
             .collect::<DbResult<Vec<_>>>()?;
@@ -0,0 +1,238 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::{
+    backend::{Pool, Row, RowExt, query},
+    common::parse_uuid,
+};
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{
+            CursorDirection, ListParams, ListResult, PageCursors, UsageAnomalyRepo,
+            cursor_from_row, truncate_to_millis,
+        },
+    },
+    models::{CreateUsageAnomaly, UsageAnomaly, UsageAnomalyFilter},
+};
+
+const COLUMNS: &str = "id, kind, org_id, api_key_id, detail, summary, observed, expected, \
+                       score, window_start, window_end, rate_limited_until, acknowledged_at, \
+                       acknowledged_by, created_at";
+
+/// Filter on `UsageAnomalyFilter`; each condition takes its parameter twice.
+const FILTER: &str = "(? IS NULL OR org_id = ?) AND (? IS NULL OR api_key_id = ?) \
+                      AND (? IS NULL OR kind = ?) AND (? = 0 OR acknowledged_at IS NULL)";
+
+pub struct SqliteUsageAnomalyRepo {
+    pool: Pool,
+}
+
+impl SqliteUsageAnomalyRepo {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    fn parse_anomaly(row: &Row) -> DbResult<UsageAnomaly> {
+        let optional_uuid = |name: &str| {
+            row.col::<Option<String>>(name)
+                .map(|s| parse_uuid(&s))
+                .transpose()
+        };
+        Ok(UsageAnomaly {
+            id: parse_uuid(&row.col::<String>("id"))?,
+            kind: row
+                .col::<String>("kind")
+                .parse()
+                .map_err(DbError::Internal)?,
+            org_id: optional_uuid("org_id")?,
+            api_key_id: optional_uuid("api_key_id")?,
+            detail: row.col("detail"),
+            summary: row.col("summary"),
+            observed: row.col("observed"),
+            expected: row.col("expected"),
+            score: row.col("score"),
+            window_start: row.col("window_start"),
+            window_end: row.col("window_end"),
+            rate_limited_until: row.col("rate_limited_until"),
+            acknowledged_at: row.col("acknowledged_at"),
+            acknowledged_by: optional_uuid("acknowledged_by")?,
+            created_at: row.col("created_at"),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl UsageAnomalyRepo for SqliteUsageAnomalyRepo {
+    async fn create(&self, input: CreateUsageAnomaly) -> DbResult<UsageAnomaly> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+
+        query(
+            r#"
+            INSERT INTO usage_anomalies (
+                id, kind, org_id, api_key_id, detail, summary, observed, expected, score,
+                window_start, window_end, fingerprint, rate_limited_until, created_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(id.to_string())
+        .bind(input.kind.as_str())
+        .bind(&input.org_id.map(|id| id.to_string()))
+        .bind(&input.api_key_id.map(|id| id.to_string()))
+        .bind(&input.detail)
+        .bind(&input.summary)
+        .bind(input.observed)
+        .bind(input.expected)
+        .bind(input.score)
+        .bind(input.window_start)
+        .bind(input.window_end)
+        .bind(&input.fingerprint)
+        .bind(&input.rate_limited_until)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(UsageAnomaly {
+            id,
+            kind: input.kind,
+            org_id: input.org_id,
+            api_key_id: input.api_key_id,
+            detail: input.detail,
+            summary: input.summary,
+            observed: input.observed,
+            expected: input.expected,
+            score: input.score,
+            window_start: input.window_start,
+            window_end: input.window_end,
+            rate_limited_until: input.rate_limited_until,
+            acknowledged_at: None,
+            acknowledged_by: None,
+            created_at: now,
+        })
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<UsageAnomaly>> {
+        let sql = format!("SELECT {COLUMNS} FROM usage_anomalies WHERE id = ?");
+        let row = query(&sql)
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|r| Self::parse_anomaly(&r)).transpose()
+    }
+
+    async fn list(
+        &self,
+        filter: &UsageAnomalyFilter,
+        params: ListParams,
+    ) -> DbResult<ListResult<UsageAnomaly>> {
+        let limit = params.limit.unwrap_or(100);
+        let fetch_limit = limit + 1;
+
+        let (comparison, order, should_reverse) =
+            params.sort_order.cursor_query_params(params.direction);
+        let (cursor_clause, order, should_reverse) = if params.cursor.is_some() {
+            (
+                format!("AND (created_at, id) {} (?, ?)", comparison),
+                order,
+                should_reverse,
+            )
+        } else {
+            (String::new(), params.sort_order.as_sql(), false)
+        };
+
+        let sql = format!(
+            r#"
+            SELECT {COLUMNS}
+            FROM usage_anomalies
+            WHERE {FILTER} {cursor_clause}
+            ORDER BY created_at {order}, id {order}
+            LIMIT ?
+            "#
+        );
+
+        let org_id = filter.org_id.map(|id| id.to_string());
+        let api_key_id = filter.api_key_id.map(|id| id.to_string());
+        let kind = filter.kind.map(|k| k.as_str());
+        let mut q = query(&sql)
+            .bind(&org_id)
+            .bind(&org_id)
+            .bind(&api_key_id)
+            .bind(&api_key_id)
+            .bind(kind)
+            .bind(kind)
+            .bind(filter.unacknowledged);
+        if let Some(ref cursor) = params.cursor {
+            q = q.bind(cursor.created_at).bind(cursor.id.to_string());
+        }
+        let rows = q.bind(fetch_limit).fetch_all(&self.pool).await?;
+
+        let has_more = rows.len() as i64 > limit;
+        let mut items = rows
+            .iter()
+            .take(limit as usize)
+            .map(Self::parse_anomaly)
+            .collect::<DbResult<Vec<_>>>()?;
+
+        if should_reverse {
+            items.reverse();
+        }
+
+        let direction = if params.cursor.is_some() {
+            params.direction
+        } else {
+            CursorDirection::Forward
+        };
+        let cursors = PageCursors::from_items(
+            &items,
+            has_more,
+            direction,
+            params.cursor.as_ref(),
+            |anomaly| cursor_from_row(anomaly.created_at, anomaly.id),
+        );
+
+        Ok(ListResult::new(items, has_more, cursors))
+    }
+
+    async fn exists_since(&self, fingerprint: &str, since: DateTime<Utc>) -> DbResult<bool> {
+        let row = query(
+            r#"
+            SELECT COUNT(*) as count
+            FROM usage_anomalies
+            WHERE fingerprint = ? AND created_at >= ?
+            "#,
+        )
+        .bind(fingerprint)
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.col::<i64>("count") > 0)
+    }
+
+    async fn acknowledge(
+        &self,
+        id: Uuid,
+        acknowledged_by: Option<Uuid>,
+    ) -> DbResult<Option<UsageAnomaly>> {
+        let now = truncate_to_millis(Utc::now());
+        query(
+            r#"
+            UPDATE usage_anomalies
+            SET acknowledged_at = ?, acknowledged_by = ?
+            WHERE id = ? AND acknowledged_at IS NULL
+            "#,
+        )
+        .bind(now)
+        .bind(acknowledged_by.map(|id| id.to_string()))
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        self.get_by_id(id).await
+    }
+}
@@ -570,6 +570,32 @@ impl UserRepo for SqliteUserRepo {
         Ok(row.col::<i64>("count"))
     }
 
+    async fn list_org_member_emails(
+        &self,
+        org_id: Uuid,
+        roles: &[String],
+    ) -> DbResult<Vec<String>> {
+        if roles.is_empty() {
+            return Ok(Vec::new());
+        }
+        let placeholders = vec!["?"; roles.len()].join(", ");
+        let sql = format!(
+            r#"
+            SELECT DISTINCT u.email
+            FROM users u
+            INNER JOIN org_memberships om ON u.id = om.user_id
+            WHERE om.org_id = ? AND om.role IN ({placeholders}) AND u.email IS NOT NULL
+            ORDER BY u.email
+            "#
+        );
+        let mut q = query(&sql).bind(org_id.to_string());
+        for role in roles {
+            q = q.bind(role);
+        }
+        let rows = q.fetch_all(&self.pool).await?;
+        Ok(rows.into_iter().map(|row| row.col("email")).collect())
+    }
+
     async fn count_total_org_memberships(&self) -> DbResult<i64> {
         let row = query("SELECT COUNT(*) as count FROM org_memberships")
             .fetch_one(&self.pool)
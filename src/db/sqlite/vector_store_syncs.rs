@@ -0,0 +1,307 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use super::{
+    backend::{Pool, Row, RowExt, query},
+    common::parse_uuid,
+};
+use crate::{
+    db::{
+        error::{DbError, DbResult},
+        repos::{
+            CursorDirection, ListParams, ListResult, PageCursors, VectorStoreSyncRepo,
+            cursor_from_row, truncate_to_millis,
+        },
+    },
+    models::{
+        CreateVectorStoreSync, VectorStoreSync, VectorStoreSyncCounts, VectorStoreSyncItem,
+        VectorStoreSyncStatus,
+    },
+};
+
+const COLUMNS: &str = "id, vector_store_id, source_name, connector, status, files_added, \
+                       files_updated, files_deleted, files_unchanged, files_failed, error, \
+                       started_at, finished_at";
+
+pub struct SqliteVectorStoreSyncRepo {
+    pool: Pool,
+}
+
+impl SqliteVectorStoreSyncRepo {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    fn parse_sync(row: &Row) -> DbResult<VectorStoreSync> {
+        Ok(VectorStoreSync {
+            id: parse_uuid(&row.col::<String>("id"))?,
+            vector_store_id: parse_uuid(&row.col::<String>("vector_store_id"))?,
+            source_name: row.col("source_name"),
+            connector: row.col("connector"),
+            status: row
+                .col::<String>("status")
+                .parse()
+                .map_err(DbError::Internal)?,
+            files_added: row.col("files_added"),
+            files_updated: row.col("files_updated"),
+            files_deleted: row.col("files_deleted"),
+            files_unchanged: row.col("files_unchanged"),
+            files_failed: row.col("files_failed"),
+            error: row.col("error"),
+            started_at: row.col("started_at"),
+            finished_at: row.col("finished_at"),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl VectorStoreSyncRepo for SqliteVectorStoreSyncRepo {
+    async fn create(&self, input: CreateVectorStoreSync) -> DbResult<VectorStoreSync> {
+        let id = Uuid::new_v4();
+        let now = truncate_to_millis(Utc::now());
+        let status = VectorStoreSyncStatus::Running;
+
+        query(
+            r#"
+            INSERT INTO vector_store_syncs (id, vector_store_id, source_name, connector, status, started_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(id.to_string())
+        .bind(input.vector_store_id.to_string())
+        .bind(&input.source_name)
+        .bind(&input.connector)
+        .bind(status.as_str())
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(VectorStoreSync {
+            id,
+            vector_store_id: input.vector_store_id,
+            source_name: input.source_name,
+            connector: input.connector,
+            status,
+            files_added: 0,
+            files_updated: 0,
+            files_deleted: 0,
+            files_unchanged: 0,
+            files_failed: 0,
+            error: None,
+            started_at: now,
+            finished_at: None,
+        })
+    }
+
+    async fn finish(
+        &self,
+        id: Uuid,
+        status: VectorStoreSyncStatus,
+        counts: &VectorStoreSyncCounts,
+        error: Option<&str>,
+    ) -> DbResult<()> {
+        let now = truncate_to_millis(Utc::now());
+
+        let result = query(
+            r#"
+            UPDATE vector_store_syncs
+            SET status = ?, files_added = ?, files_updated = ?, files_deleted = ?,
+                files_unchanged = ?, files_failed = ?, error = ?, finished_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(status.as_str())
+        .bind(counts.files_added)
+        .bind(counts.files_updated)
+        .bind(counts.files_deleted)
+        .bind(counts.files_unchanged)
+        .bind(counts.files_failed)
+        .bind(error)
+        .bind(now)
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+        Ok(())
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> DbResult<Option<VectorStoreSync>> {
+        let sql = format!("SELECT {COLUMNS} FROM vector_store_syncs WHERE id = ?");
+        let row = query(&sql)
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|r| Self::parse_sync(&r)).transpose()
+    }
+
+    async fn list(
+        &self,
+        vector_store_id: Uuid,
+        params: ListParams,
+    ) -> DbResult<ListResult<VectorStoreSync>> {
+        let limit = params.limit.unwrap_or(100);
+        let fetch_limit = limit + 1;
+
+        let (comparison, order, should_reverse) =
+            params.sort_order.cursor_query_params(params.direction);
+        let (cursor_clause, order, should_reverse) = if params.cursor.is_some() {
+            (
+                format!("AND (started_at, id) {} (?, ?)", comparison),
+                order,
+                should_reverse,
+            )
+        } else {
+            (String::new(), params.sort_order.as_sql(), false)
+        };
+
+        let sql = format!(
+            r#"
+            SELECT {COLUMNS}
+            FROM vector_store_syncs
+            WHERE vector_store_id = ? {cursor_clause}
+            ORDER BY started_at {order}, id {order}
+            LIMIT ?
+            "#
+        );
+
+        let mut q = query(&sql).bind(vector_store_id.to_string());
+        if let Some(ref cursor) = params.cursor {
+            q = q.bind(cursor.created_at).bind(cursor.id.to_string());
+        }
+        let rows = q.bind(fetch_limit).fetch_all(&self.pool).await?;
+
+        let has_more = rows.len() as i64 > limit;
+        let mut items = rows
+            .iter()
+            .take(limit as usize)
+            .map(Self::parse_sync)
+            .collect::<DbResult<Vec<_>>>()?;
+
+        if should_reverse {
+            items.reverse();
+        }
+
+        let direction = if params.cursor.is_some() {
+            params.direction
+        } else {
+            CursorDirection::Forward
+        };
+        let cursors = PageCursors::from_items(
+            &items,
+            has_more,
+            direction,
+            params.cursor.as_ref(),
+            |sync| cursor_from_row(sync.started_at, sync.id),
+        );
+
+        Ok(ListResult::new(items, has_more, cursors))
+    }
+
+    async fn latest_for_source(
+        &self,
+        vector_store_id: Uuid,
+        source_name: &str,
+    ) -> DbResult<Option<VectorStoreSync>> {
+        let sql = format!(
+            r#"
+            SELECT {COLUMNS}
+            FROM vector_store_syncs
+            WHERE vector_store_id = ? AND source_name = ?
+            ORDER BY started_at DESC, id DESC
+            LIMIT 1
+            "#
+        );
+        let row = query(&sql)
+            .bind(vector_store_id.to_string())
+            .bind(source_name)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|r| Self::parse_sync(&r)).transpose()
+    }
+
+    async fn list_items(
+        &self,
+        vector_store_id: Uuid,
+        source_name: &str,
+    ) -> DbResult<Vec<VectorStoreSyncItem>> {
+        let rows = query(
+            r#"
+            SELECT source_key, version, file_id
+            FROM vector_store_sync_items
+            WHERE vector_store_id = ? AND source_name = ?
+            "#,
+        )
+        .bind(vector_store_id.to_string())
+        .bind(source_name)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(VectorStoreSyncItem {
+                    source_key: row.col("source_key"),
+                    version: row.col("version"),
+                    file_id: parse_uuid(&row.col::<String>("file_id"))?,
+                })
+            })
+            .collect()
+    }
+
+    async fn upsert_item(
+        &self,
+        vector_store_id: Uuid,
+        source_name: &str,
+        item: &VectorStoreSyncItem,
+    ) -> DbResult<()> {
+        let now = truncate_to_millis(Utc::now());
+
+        query(
+            r#"
+            INSERT INTO vector_store_sync_items (vector_store_id, source_name, source_key, version, file_id, synced_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            ON CONFLICT (vector_store_id, source_name, source_key) DO UPDATE SET
+                version = excluded.version,
+                file_id = excluded.file_id,
+                synced_at = excluded.synced_at
+            "#,
+        )
+        .bind(vector_store_id.to_string())
+        .bind(source_name)
+        .bind(&item.source_key)
+        .bind(&item.version)
+        .bind(item.file_id.to_string())
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete_item(
+        &self,
+        vector_store_id: Uuid,
+        source_name: &str,
+        source_key: &str,
+    ) -> DbResult<()> {
+        query(
+            r#"
+            DELETE FROM vector_store_sync_items
+            WHERE vector_store_id = ? AND source_name = ? AND source_key = ?
+            "#,
+        )
+        .bind(vector_store_id.to_string())
+        .bind(source_name)
+        .bind(source_key)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
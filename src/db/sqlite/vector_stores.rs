@@ -18,9 +18,9 @@ use crate::{
     },
     models::{
         AddFileToVectorStore, ChunkingStrategy, CreateVectorStore, ExpiresAfter, FileCounts,
-        FileError, OBJECT_TYPE_VECTOR_STORE, OBJECT_TYPE_VECTOR_STORE_FILE, UpdateVectorStore,
-        VectorStore, VectorStoreFile, VectorStoreFileStatus, VectorStoreOwnerType,
-        VectorStoreStatus,
+        FileError, HybridSearchOptions, OBJECT_TYPE_VECTOR_STORE, OBJECT_TYPE_VECTOR_STORE_FILE,
+        UpdateVectorStore, VectorStore, VectorStoreFile, VectorStoreFileStatus,
+        VectorStoreOwnerType, VectorStoreStatus,
     },
 };
 
@@ -53,6 +53,13 @@ impl SqliteVectorStoresRepo {
         }
     }
 
+    fn parse_hybrid_search(json_str: Option<String>) -> DbResult<Option<HybridSearchOptions>> {
+        match json_str {
+            Some(s) => serde_json::from_str(&s).map_err(|e| DbError::Internal(e.to_string())),
+            None => Ok(None),
+        }
+    }
+
     fn parse_chunking_strategy(json_str: Option<String>) -> DbResult<Option<ChunkingStrategy>> {
         match json_str {
             Some(s) => serde_json::from_str(&s).map_err(|e| DbError::Internal(e.to_string())),
@@ -75,8 +82,8 @@ impl SqliteVectorStoresRepo {
 
     /// Parse a VectorStore from a database row.
     /// Expects columns: id, owner_type, owner_id, name, description, status, embedding_model,
-    /// embedding_dimensions, usage_bytes, file_counts, metadata, expires_after, expires_at,
-    /// last_active_at, created_at, updated_at
+    /// embedding_dimensions, usage_bytes, file_counts, metadata, expires_after, hybrid_search,
+    /// expires_at, last_active_at, created_at, updated_at
     fn vector_store_from_row(row: &Row) -> DbResult<VectorStore> {
         let owner_type_str: String = row.col("owner_type");
         let status_str: String = row.col("status");
@@ -100,6 +107,7 @@ impl SqliteVectorStoresRepo {
             file_counts: Self::parse_file_counts(&file_counts_str)?,
             metadata: Self::parse_metadata(row.col("metadata"))?,
             expires_after: Self::parse_expires_after(row.col("expires_after"))?,
+            hybrid_search: Self::parse_hybrid_search(row.col("hybrid_search"))?,
             expires_at: row.col("expires_at"),
             last_active_at: row.col("last_active_at"),
             created_at: row.col("created_at"),
@@ -165,13 +173,22 @@ impl VectorStoresRepo for SqliteVectorStoresRepo {
             .transpose()
             .map_err(|e| DbError::Internal(e.to_string()))?;
 
+        let hybrid_search_json = input
+            .hybrid_search
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| DbError::Internal(e.to_string()))?;
+
+        let expires_at = input.expires_after.as_ref().map(|e| e.expires_at(now));
+
         let default_file_counts =
             r#"{"cancelled":0,"completed":0,"failed":0,"in_progress":0,"total":0}"#;
 
         query(
             r#"
-            INSERT INTO vector_stores (id, owner_type, owner_id, name, description, embedding_model, embedding_dimensions, metadata, expires_after, file_counts, created_at, updated_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO vector_stores (id, owner_type, owner_id, name, description, embedding_model, embedding_dimensions, metadata, expires_after, hybrid_search, expires_at, last_active_at, file_counts, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(id.to_string())
@@ -183,6 +200,9 @@ impl VectorStoresRepo for SqliteVectorStoresRepo {
         .bind(input.embedding_dimensions)
         .bind(&metadata_json)
         .bind(&expires_after_json)
+        .bind(&hybrid_search_json)
+        .bind(expires_at)
+        .bind(now)
         .bind(default_file_counts)
         .bind(now)
         .bind(now)
@@ -203,8 +223,9 @@ impl VectorStoresRepo for SqliteVectorStoresRepo {
             file_counts: FileCounts::default(),
             metadata: Self::parse_metadata(metadata_json)?,
             expires_after: input.expires_after,
-            expires_at: None,
-            last_active_at: None,
+            hybrid_search: input.hybrid_search,
+            expires_at,
+            last_active_at: Some(now),
             created_at: now,
             updated_at: now,
         })
@@ -214,7 +235,7 @@ impl VectorStoresRepo for SqliteVectorStoresRepo {
         let result = query(
             r#"
             SELECT id, owner_type, owner_id, name, description, status, embedding_model, embedding_dimensions,
-                   usage_bytes, file_counts, metadata, expires_after, expires_at, last_active_at, created_at, updated_at
+                   usage_bytes, file_counts, metadata, expires_after, hybrid_search, expires_at, last_active_at, created_at, updated_at
             FROM vector_stores
             WHERE id = ? AND deleted_at IS NULL
             "#,
@@ -233,7 +254,7 @@ impl VectorStoresRepo for SqliteVectorStoresRepo {
         let result = query(
             r#"
             SELECT vs.id, vs.owner_type, vs.owner_id, vs.name, vs.description, vs.status, vs.embedding_model, vs.embedding_dimensions,
-                   vs.usage_bytes, vs.file_counts, vs.metadata, vs.expires_after, vs.expires_at, vs.last_active_at, vs.created_at, vs.updated_at
+                   vs.usage_bytes, vs.file_counts, vs.metadata, vs.expires_after, vs.hybrid_search, vs.expires_at, vs.last_active_at, vs.created_at, vs.updated_at
             FROM vector_stores vs
             WHERE vs.id = ? AND vs.deleted_at IS NULL
             AND (
@@ -276,7 +297,7 @@ impl VectorStoresRepo for SqliteVectorStoresRepo {
         let result = query(
             r#"
             SELECT id, owner_type, owner_id, name, description, status, embedding_model, embedding_dimensions,
-                   usage_bytes, file_counts, metadata, expires_after, expires_at, last_active_at, created_at, updated_at
+                   usage_bytes, file_counts, metadata, expires_after, hybrid_search, expires_at, last_active_at, created_at, updated_at
             FROM vector_stores
             WHERE owner_type = ? AND owner_id = ? AND name = ? AND deleted_at IS NULL
             "#,
@@ -316,7 +337,7 @@ impl VectorStoresRepo for SqliteVectorStoresRepo {
             let sql = format!(
                 r#"
                 SELECT id, owner_type, owner_id, name, description, status, embedding_model, embedding_dimensions,
-                       usage_bytes, file_counts, metadata, expires_after, expires_at, last_active_at, created_at, updated_at
+                       usage_bytes, file_counts, metadata, expires_after, hybrid_search, expires_at, last_active_at, created_at, updated_at
                 FROM vector_stores
                 WHERE owner_type = ? AND owner_id = ?
                 AND (updated_at, id) {} (?, ?)
@@ -364,7 +385,7 @@ impl VectorStoresRepo for SqliteVectorStoresRepo {
             format!(
                 r#"
                 SELECT id, owner_type, owner_id, name, description, status, embedding_model, embedding_dimensions,
-                       usage_bytes, file_counts, metadata, expires_after, expires_at, last_active_at, created_at, updated_at
+                       usage_bytes, file_counts, metadata, expires_after, hybrid_search, expires_at, last_active_at, created_at, updated_at
                 FROM vector_stores
                 WHERE owner_type = ? AND owner_id = ?
                 ORDER BY updated_at {}, id {}
@@ -376,7 +397,7 @@ impl VectorStoresRepo for SqliteVectorStoresRepo {
             format!(
                 r#"
                 SELECT id, owner_type, owner_id, name, description, status, embedding_model, embedding_dimensions,
-                       usage_bytes, file_counts, metadata, expires_after, expires_at, last_active_at, created_at, updated_at
+                       usage_bytes, file_counts, metadata, expires_after, hybrid_search, expires_at, last_active_at, created_at, updated_at
                 FROM vector_stores
                 WHERE owner_type = ? AND owner_id = ? AND deleted_at IS NULL
                 ORDER BY updated_at {}, id {}
@@ -472,7 +493,7 @@ impl VectorStoresRepo for SqliteVectorStoresRepo {
             let sql = format!(
                 r#"
                 SELECT id, owner_type, owner_id, name, description, status, embedding_model, embedding_dimensions,
-                       usage_bytes, file_counts, metadata, expires_after, expires_at, last_active_at, created_at, updated_at
+                       usage_bytes, file_counts, metadata, expires_after, hybrid_search, expires_at, last_active_at, created_at, updated_at
                 FROM vector_stores
                 WHERE ({})
                 AND (updated_at, id) {} (?, ?)
@@ -522,7 +543,7 @@ impl VectorStoresRepo for SqliteVectorStoresRepo {
             format!(
                 r#"
                 SELECT id, owner_type, owner_id, name, description, status, embedding_model, embedding_dimensions,
-                       usage_bytes, file_counts, metadata, expires_after, expires_at, last_active_at, created_at, updated_at
+                       usage_bytes, file_counts, metadata, expires_after, hybrid_search, expires_at, last_active_at, created_at, updated_at
                 FROM vector_stores
                 WHERE ({})
                 ORDER BY updated_at {}, id {}
@@ -534,7 +555,7 @@ impl VectorStoresRepo for SqliteVectorStoresRepo {
             format!(
                 r#"
                 SELECT id, owner_type, owner_id, name, description, status, embedding_model, embedding_dimensions,
-                       usage_bytes, file_counts, metadata, expires_after, expires_at, last_active_at, created_at, updated_at
+                       usage_bytes, file_counts, metadata, expires_after, hybrid_search, expires_at, last_active_at, created_at, updated_at
                 FROM vector_stores
                 WHERE ({}) AND deleted_at IS NULL
                 ORDER BY updated_at {}, id {}
@@ -593,7 +614,7 @@ impl VectorStoresRepo for SqliteVectorStoresRepo {
             let sql = format!(
                 r#"
                 SELECT id, owner_type, owner_id, name, description, status, embedding_model, embedding_dimensions,
-                       usage_bytes, file_counts, metadata, expires_after, expires_at, last_active_at, created_at, updated_at
+                       usage_bytes, file_counts, metadata, expires_after, hybrid_search, expires_at, last_active_at, created_at, updated_at
                 FROM vector_stores
                 WHERE (updated_at, id) {} (?, ?)
                 {}
@@ -637,7 +658,7 @@ impl VectorStoresRepo for SqliteVectorStoresRepo {
             format!(
                 r#"
                 SELECT id, owner_type, owner_id, name, description, status, embedding_model, embedding_dimensions,
-                       usage_bytes, file_counts, metadata, expires_after, expires_at, last_active_at, created_at, updated_at
+                       usage_bytes, file_counts, metadata, expires_after, hybrid_search, expires_at, last_active_at, created_at, updated_at
                 FROM vector_stores
                 ORDER BY updated_at {}, id {}
                 LIMIT ?
@@ -648,7 +669,7 @@ impl VectorStoresRepo for SqliteVectorStoresRepo {
             format!(
                 r#"
                 SELECT id, owner_type, owner_id, name, description, status, embedding_model, embedding_dimensions,
-                       usage_bytes, file_counts, metadata, expires_after, expires_at, last_active_at, created_at, updated_at
+                       usage_bytes, file_counts, metadata, expires_after, hybrid_search, expires_at, last_active_at, created_at, updated_at
                 FROM vector_stores
                 WHERE deleted_at IS NULL
                 ORDER BY updated_at {}, id {}
@@ -694,7 +715,7 @@ impl VectorStoresRepo for SqliteVectorStoresRepo {
             let current = query(
                 r#"
                 SELECT id, owner_type, owner_id, name, description, status, embedding_model, embedding_dimensions,
-                       usage_bytes, file_counts, metadata, expires_after, expires_at, last_active_at, created_at, updated_at
+                       usage_bytes, file_counts, metadata, expires_after, hybrid_search, expires_at, last_active_at, created_at, updated_at
                 FROM vector_stores
                 WHERE id = ? AND deleted_at IS NULL
                 "#,
@@ -718,6 +739,7 @@ impl VectorStoresRepo for SqliteVectorStoresRepo {
             let current_description: Option<String> = current.col("description");
             let current_metadata: Option<String> = current.col("metadata");
             let current_expires_after: Option<String> = current.col("expires_after");
+            let current_hybrid_search: Option<String> = current.col("hybrid_search");
 
             let new_name = input.name.unwrap_or(current_name);
             let new_description = input.description.or(current_description);
@@ -727,17 +749,29 @@ impl VectorStoresRepo for SqliteVectorStoresRepo {
                 .transpose()
                 .map_err(|e| DbError::Internal(e.to_string()))?
                 .or(current_metadata);
+            let last_active_at: Option<DateTime<Utc>> = current.col("last_active_at");
+            // A new policy is measured from the store's current anchor
+            let new_expires_at = match input.expires_after {
+                Some(ref policy) => Some(policy.expires_at(last_active_at.unwrap_or(now))),
+                None => current.col("expires_at"),
+            };
             let new_expires_after = input
                 .expires_after
                 .map(|e| serde_json::to_string(&e))
                 .transpose()
                 .map_err(|e| DbError::Internal(e.to_string()))?
                 .or(current_expires_after);
+            let new_hybrid_search = input
+                .hybrid_search
+                .map(|h| serde_json::to_string(&h))
+                .transpose()
+                .map_err(|e| DbError::Internal(e.to_string()))?
+                .or(current_hybrid_search);
 
             let update_result = query(
                 r#"
                 UPDATE vector_stores
-                SET name = ?, description = ?, metadata = ?, expires_after = ?, updated_at = ?
+                SET name = ?, description = ?, metadata = ?, expires_after = ?, hybrid_search = ?, expires_at = ?, updated_at = ?
                 WHERE id = ? AND deleted_at IS NULL
                 "#,
             )
@@ -745,6 +779,8 @@ impl VectorStoresRepo for SqliteVectorStoresRepo {
             .bind(&new_description)
             .bind(&new_metadata)
             .bind(&new_expires_after)
+            .bind(&new_hybrid_search)
+            .bind(new_expires_at)
             .bind(now)
             .bind(id.to_string())
             .execute(&mut *conn)
@@ -770,8 +806,9 @@ impl VectorStoresRepo for SqliteVectorStoresRepo {
                 file_counts: Self::parse_file_counts(&file_counts_str)?,
                 metadata: Self::parse_metadata(new_metadata)?,
                 expires_after: Self::parse_expires_after(new_expires_after)?,
-                expires_at: current.col("expires_at"),
-                last_active_at: current.col("last_active_at"),
+                hybrid_search: Self::parse_hybrid_search(new_hybrid_search)?,
+                expires_at: new_expires_at,
+                last_active_at,
                 created_at: current.col("created_at"),
                 updated_at: now,
             })
@@ -850,7 +887,7 @@ impl VectorStoresRepo for SqliteVectorStoresRepo {
         let rows = query(
             r#"
             SELECT id, owner_type, owner_id, name, description, status, embedding_model, embedding_dimensions,
-                   usage_bytes, file_counts, metadata, expires_after, expires_at, last_active_at, created_at, updated_at
+                   usage_bytes, file_counts, metadata, expires_after, hybrid_search, expires_at, last_active_at, created_at, updated_at
             FROM vector_stores
             WHERE deleted_at IS NOT NULL AND deleted_at < ?
             "#,
@@ -867,26 +904,53 @@ impl VectorStoresRepo for SqliteVectorStoresRepo {
     async fn touch_vector_store(&self, id: Uuid) -> DbResult<()> {
         let now = truncate_to_millis(chrono::Utc::now());
 
-        let result = query(
+        let row =
+            query("SELECT expires_after FROM vector_stores WHERE id = ? AND deleted_at IS NULL")
+                .bind(id.to_string())
+                .fetch_optional(&self.pool)
+                .await?
+                .ok_or(DbError::NotFound)?;
+        let expires_at = Self::parse_expires_after(row.col("expires_after"))?
+            .map(|policy| policy.expires_at(now));
+
+        // Expired stores stay expired; activity doesn't revive them
+        query(
             r#"
             UPDATE vector_stores
-            SET last_active_at = ?, updated_at = ?
-            WHERE id = ? AND deleted_at IS NULL
+            SET last_active_at = ?, expires_at = ?, updated_at = ?
+            WHERE id = ? AND deleted_at IS NULL AND status != 'expired'
             "#,
         )
         .bind(now)
+        .bind(expires_at)
         .bind(now)
         .bind(id.to_string())
         .execute(&self.pool)
         .await?;
 
-        if result.rows_affected() == 0 {
-            return Err(DbError::NotFound);
-        }
-
         Ok(())
     }
 
+    async fn expire_vector_stores(&self, now: DateTime<Utc>) -> DbResult<u64> {
+        let now = truncate_to_millis(now);
+        let result = query(
+            r#"
+            UPDATE vector_stores
+            SET status = 'expired', updated_at = ?
+            WHERE deleted_at IS NULL
+              AND status != 'expired'
+              AND expires_at IS NOT NULL
+              AND expires_at <= ?
+            "#,
+        )
+        .bind(now)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
     // ==================== VectorStore Files CRUD ====================
 
     async fn add_file_to_vector_store(
@@ -1362,6 +1426,7 @@ mod tests {
                 file_counts TEXT NOT NULL DEFAULT '{"cancelled":0,"completed":0,"failed":0,"in_progress":0,"total":0}',
                 metadata TEXT,
                 expires_after TEXT,
+                hybrid_search TEXT,
                 expires_at TEXT,
                 last_active_at TEXT,
                 created_at TEXT NOT NULL DEFAULT (datetime('now')),
@@ -1465,6 +1530,7 @@ mod tests {
             embedding_dimensions: 1536,
             metadata: None,
             expires_after: None,
+            hybrid_search: None,
             chunking_strategy: None,
         };
 
@@ -1501,6 +1567,7 @@ mod tests {
             embedding_dimensions: 1536,
             metadata: None,
             expires_after: None,
+            hybrid_search: None,
             chunking_strategy: None,
         };
 
@@ -1534,6 +1601,7 @@ mod tests {
             embedding_dimensions: 1536,
             metadata: None,
             expires_after: None,
+            hybrid_search: None,
             chunking_strategy: None,
         };
 
@@ -1568,6 +1636,7 @@ mod tests {
                 embedding_dimensions: 1536,
                 metadata: None,
                 expires_after: None,
+                hybrid_search: None,
                 chunking_strategy: None,
             };
             repo.create_vector_store(input)
@@ -1598,6 +1667,7 @@ mod tests {
             embedding_dimensions: 1536,
             metadata: None,
             expires_after: None,
+            hybrid_search: None,
             chunking_strategy: None,
         };
 
@@ -1614,6 +1684,7 @@ mod tests {
                     description: Some("New description".to_string()),
                     metadata: None,
                     expires_after: None,
+                    hybrid_search: None,
                 },
             )
             .await
@@ -1638,6 +1709,7 @@ mod tests {
             embedding_dimensions: 1536,
             metadata: None,
             expires_after: None,
+            hybrid_search: None,
             chunking_strategy: None,
         };
 
@@ -1658,6 +1730,60 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[tokio::test]
+    async fn test_expire_vector_stores() {
+        let pool = create_test_pool().await;
+        let repo = SqliteVectorStoresRepo::new(pool);
+
+        let user_id = Uuid::new_v4();
+        let input = CreateVectorStore {
+            owner: VectorStoreOwner::User { user_id },
+            file_ids: vec![],
+            name: Some("Expiring".to_string()),
+            description: None,
+            embedding_model: "text-embedding-3-small".to_string(),
+            embedding_dimensions: 1536,
+            metadata: None,
+            expires_after: Some(ExpiresAfter {
+                anchor: "last_active_at".to_string(),
+                days: 7,
+            }),
+            hybrid_search: None,
+            chunking_strategy: None,
+        };
+
+        let created = repo
+            .create_vector_store(input)
+            .await
+            .expect("Failed to create vector store");
+        let expires_at = created.expires_at.expect("expires_at should be set");
+
+        // Nothing is due yet
+        let expired = repo
+            .expire_vector_stores(expires_at - chrono::Duration::seconds(1))
+            .await
+            .expect("Failed to expire vector stores");
+        assert_eq!(expired, 0);
+
+        let expired = repo
+            .expire_vector_stores(expires_at)
+            .await
+            .expect("Failed to expire vector stores");
+        assert_eq!(expired, 1);
+
+        // Activity after expiry doesn't revive the store
+        repo.touch_vector_store(created.id)
+            .await
+            .expect("Touch should succeed");
+        let fetched = repo
+            .get_vector_store(created.id)
+            .await
+            .expect("Query should succeed")
+            .expect("VectorStore should exist");
+        assert_eq!(fetched.status, VectorStoreStatus::Expired);
+        assert_eq!(fetched.expires_at, Some(expires_at));
+    }
+
     #[tokio::test]
     async fn test_add_and_get_vector_store_file() {
         let pool = create_test_pool().await;
@@ -1673,6 +1799,7 @@ mod tests {
             embedding_dimensions: 1536,
             metadata: None,
             expires_after: None,
+            hybrid_search: None,
             chunking_strategy: None,
         };
 
@@ -1729,6 +1856,7 @@ mod tests {
             embedding_dimensions: 1536,
             metadata: None,
             expires_after: None,
+            hybrid_search: None,
             chunking_strategy: None,
         };
 
@@ -1795,6 +1923,7 @@ mod tests {
             embedding_dimensions: 1536,
             metadata: None,
             expires_after: None,
+            hybrid_search: None,
             chunking_strategy: None,
         };
 
@@ -1857,6 +1986,7 @@ mod tests {
             embedding_dimensions: 1536,
             metadata: None,
             expires_after: None,
+            hybrid_search: None,
             chunking_strategy: None,
         };
         let other_vector_store = repo
@@ -1889,6 +2019,7 @@ mod tests {
             embedding_dimensions: 1536,
             metadata: None,
             expires_after: None,
+            hybrid_search: None,
             chunking_strategy: None,
         };
 
@@ -1976,6 +2107,7 @@ mod tests {
             embedding_dimensions: 1536,
             metadata: None,
             expires_after: None,
+            hybrid_search: None,
             chunking_strategy: None,
         };
 
@@ -2073,6 +2205,7 @@ mod tests {
             embedding_dimensions: 1536,
             metadata: None,
             expires_after: None,
+            hybrid_search: None,
             chunking_strategy: None,
         };
 
@@ -0,0 +1,166 @@
+//! Shared tests for AdminPasskeyRepo implementations
+
+use uuid::Uuid;
+
+use crate::{
+    db::{DbError, repos::AdminPasskeyRepo},
+    models::CreateAdminPasskey,
+};
+
+fn passkey(user_id: Uuid, credential_id: &str, name: &str) -> CreateAdminPasskey {
+    CreateAdminPasskey {
+        user_id,
+        credential_id: credential_id.to_string(),
+        name: name.to_string(),
+        credential: format!(r#"{{"cred":"{credential_id}"}}"#),
+    }
+}
+
+pub async fn passkey_lifecycle(repo: &dyn AdminPasskeyRepo, user_id: Uuid) {
+    assert!(repo.list_by_user(user_id).await.unwrap().is_empty());
+
+    let first = repo
+        .create(passkey(user_id, "cred-a", "YubiKey"))
+        .await
+        .unwrap();
+    let second = repo
+        .create(passkey(user_id, "cred-b", "Laptop"))
+        .await
+        .unwrap();
+    assert!(first.last_used_at.is_none());
+
+    let listed = repo.list_by_user(user_id).await.unwrap();
+    assert_eq!(listed.len(), 2);
+    assert!(listed.iter().all(|p| p.user_id == user_id));
+    assert!(listed.iter().any(|p| p.id == first.id));
+
+    let found = repo
+        .get_by_credential_id("cred-b")
+        .await
+        .unwrap()
+        .expect("passkey by credential id");
+    assert_eq!(found.id, second.id);
+    assert_eq!(found.name, "Laptop");
+    assert_eq!(found.credential, r#"{"cred":"cred-b"}"#);
+    assert!(repo.get_by_credential_id("missing").await.unwrap().is_none());
+
+    assert!(repo.delete(user_id, first.id).await.unwrap());
+    assert!(!repo.delete(user_id, first.id).await.unwrap());
+    assert_eq!(repo.list_by_user(user_id).await.unwrap().len(), 1);
+}
+
+pub async fn duplicate_credential_id_conflicts(repo: &dyn AdminPasskeyRepo, user_id: Uuid) {
+    repo.create(passkey(user_id, "cred-a", "One")).await.unwrap();
+    let result = repo.create(passkey(user_id, "cred-a", "Two")).await;
+    assert!(matches!(result, Err(DbError::Conflict(_))));
+}
+
+pub async fn record_use_updates_credential(repo: &dyn AdminPasskeyRepo, user_id: Uuid) {
+    let created = repo
+        .create(passkey(user_id, "cred-a", "YubiKey"))
+        .await
+        .unwrap();
+
+    repo.record_use(created.id, r#"{"counter":2}"#).await.unwrap();
+
+    let stored = repo.get_by_credential_id("cred-a").await.unwrap().unwrap();
+    assert_eq!(stored.credential, r#"{"counter":2}"#);
+    assert!(stored.last_used_at.is_some());
+}
+
+pub async fn delete_is_scoped_to_owner(repo: &dyn AdminPasskeyRepo, user_id: Uuid) {
+    let created = repo
+        .create(passkey(user_id, "cred-a", "YubiKey"))
+        .await
+        .unwrap();
+
+    assert!(!repo.delete(Uuid::new_v4(), created.id).await.unwrap());
+    assert_eq!(repo.list_by_user(user_id).await.unwrap().len(), 1);
+}
+
+#[cfg(all(test, feature = "database-sqlite"))]
+mod sqlite_tests {
+    use uuid::Uuid;
+
+    use crate::{
+        db::{
+            repos::UserRepo,
+            sqlite::{SqliteAdminPasskeyRepo, SqliteUserRepo},
+            tests::harness::{create_sqlite_pool, run_sqlite_migrations},
+        },
+        models::CreateUser,
+    };
+
+    async fn create_repo() -> (SqliteAdminPasskeyRepo, Uuid) {
+        let pool = create_sqlite_pool().await;
+        run_sqlite_migrations(&pool).await;
+        let user = SqliteUserRepo::new(pool.clone())
+            .create(CreateUser {
+                external_id: "admin".to_string(),
+                email: None,
+                name: None,
+            })
+            .await
+            .expect("create user");
+        (SqliteAdminPasskeyRepo::new(pool), user.id)
+    }
+
+    macro_rules! sqlite_test {
+        ($name:ident) => {
+            #[tokio::test]
+            async fn $name() {
+                let (repo, user_id) = create_repo().await;
+                super::$name(&repo, user_id).await;
+            }
+        };
+    }
+
+    sqlite_test!(passkey_lifecycle);
+    sqlite_test!(duplicate_credential_id_conflicts);
+    sqlite_test!(record_use_updates_credential);
+    sqlite_test!(delete_is_scoped_to_owner);
+}
+
+#[cfg(all(test, feature = "database-postgres"))]
+mod postgres_tests {
+    use uuid::Uuid;
+
+    use crate::{
+        db::{
+            postgres::{PostgresAdminPasskeyRepo, PostgresUserRepo},
+            repos::UserRepo,
+            tests::harness::postgres::{create_isolated_postgres_pool, run_postgres_migrations},
+        },
+        models::CreateUser,
+    };
+
+    async fn create_repo() -> (PostgresAdminPasskeyRepo, Uuid) {
+        let pool = create_isolated_postgres_pool().await;
+        run_postgres_migrations(&pool).await;
+        let user = PostgresUserRepo::new(pool.clone(), None)
+            .create(CreateUser {
+                external_id: "admin".to_string(),
+                email: None,
+                name: None,
+            })
+            .await
+            .expect("create user");
+        (PostgresAdminPasskeyRepo::new(pool), user.id)
+    }
+
+    macro_rules! postgres_test {
+        ($name:ident) => {
+            #[tokio::test]
+            #[ignore = "Requires Docker - run with `cargo test -- --ignored`"]
+            async fn $name() {
+                let (repo, user_id) = create_repo().await;
+                super::$name(&repo, user_id).await;
+            }
+        };
+    }
+
+    postgres_test!(passkey_lifecycle);
+    postgres_test!(duplicate_credential_id_conflicts);
+    postgres_test!(record_use_updates_credential);
+    postgres_test!(delete_is_scoped_to_owner);
+}
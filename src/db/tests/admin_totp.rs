@@ -0,0 +1,144 @@
+//! Shared tests for AdminTotpRepo implementations
+
+use uuid::Uuid;
+
+use crate::db::repos::AdminTotpRepo;
+
+pub async fn enrollment_lifecycle(repo: &dyn AdminTotpRepo, user_id: Uuid) {
+    assert!(repo.get(user_id).await.unwrap().is_none());
+
+    let pending = repo.upsert_pending(user_id, "SECRET1").await.unwrap();
+    assert!(!pending.is_verified());
+
+    let stored = repo
+        .get(user_id)
+        .await
+        .unwrap()
+        .expect("pending enrollment");
+    assert_eq!(stored.secret, "SECRET1");
+    assert!(stored.verified_at.is_none());
+    assert!(stored.last_used_step.is_none());
+
+    assert!(repo.mark_verified(user_id, 100).await.unwrap());
+    // Already verified
+    assert!(!repo.mark_verified(user_id, 101).await.unwrap());
+
+    let stored = repo.get(user_id).await.unwrap().unwrap();
+    assert!(stored.is_verified());
+    assert_eq!(stored.last_used_step, Some(100));
+
+    assert!(repo.delete(user_id).await.unwrap());
+    assert!(!repo.delete(user_id).await.unwrap());
+    assert!(repo.get(user_id).await.unwrap().is_none());
+}
+
+pub async fn record_use_rejects_replayed_steps(repo: &dyn AdminTotpRepo, user_id: Uuid) {
+    repo.upsert_pending(user_id, "SECRET1").await.unwrap();
+    // Pending enrollments can't be used
+    assert!(!repo.record_use(user_id, 5).await.unwrap());
+
+    repo.mark_verified(user_id, 10).await.unwrap();
+    assert!(!repo.record_use(user_id, 10).await.unwrap());
+    assert!(!repo.record_use(user_id, 9).await.unwrap());
+    assert!(repo.record_use(user_id, 11).await.unwrap());
+    assert!(!repo.record_use(user_id, 11).await.unwrap());
+
+    let stored = repo.get(user_id).await.unwrap().unwrap();
+    assert_eq!(stored.last_used_step, Some(11));
+}
+
+pub async fn upsert_pending_resets_verification(repo: &dyn AdminTotpRepo, user_id: Uuid) {
+    repo.upsert_pending(user_id, "SECRET1").await.unwrap();
+    repo.mark_verified(user_id, 10).await.unwrap();
+
+    repo.upsert_pending(user_id, "SECRET2").await.unwrap();
+    let stored = repo.get(user_id).await.unwrap().unwrap();
+    assert_eq!(stored.secret, "SECRET2");
+    assert!(stored.verified_at.is_none());
+    assert!(stored.last_used_step.is_none());
+}
+
+#[cfg(all(test, feature = "database-sqlite"))]
+mod sqlite_tests {
+    use uuid::Uuid;
+
+    use crate::{
+        db::{
+            repos::UserRepo,
+            sqlite::{SqliteAdminTotpRepo, SqliteUserRepo},
+            tests::harness::{create_sqlite_pool, run_sqlite_migrations},
+        },
+        models::CreateUser,
+    };
+
+    async fn create_repo() -> (SqliteAdminTotpRepo, Uuid) {
+        let pool = create_sqlite_pool().await;
+        run_sqlite_migrations(&pool).await;
+        let user = SqliteUserRepo::new(pool.clone())
+            .create(CreateUser {
+                external_id: "admin".to_string(),
+                email: None,
+                name: None,
+            })
+            .await
+            .expect("create user");
+        (SqliteAdminTotpRepo::new(pool), user.id)
+    }
+
+    macro_rules! sqlite_test {
+        ($name:ident) => {
+            #[tokio::test]
+            async fn $name() {
+                let (repo, user_id) = create_repo().await;
+                super::$name(&repo, user_id).await;
+            }
+        };
+    }
+
+    sqlite_test!(enrollment_lifecycle);
+    sqlite_test!(record_use_rejects_replayed_steps);
+    sqlite_test!(upsert_pending_resets_verification);
+}
+
+#[cfg(all(test, feature = "database-postgres"))]
+mod postgres_tests {
+    use uuid::Uuid;
+
+    use crate::{
+        db::{
+            postgres::{PostgresAdminTotpRepo, PostgresUserRepo},
+            repos::UserRepo,
+            tests::harness::postgres::{create_isolated_postgres_pool, run_postgres_migrations},
+        },
+        models::CreateUser,
+    };
+
+    async fn create_repo() -> (PostgresAdminTotpRepo, Uuid) {
+        let pool = create_isolated_postgres_pool().await;
+        run_postgres_migrations(&pool).await;
+        let user = PostgresUserRepo::new(pool.clone(), None)
+            .create(CreateUser {
+                external_id: "admin".to_string(),
+                email: None,
+                name: None,
+            })
+            .await
+            .expect("create user");
+        (PostgresAdminTotpRepo::new(pool), user.id)
+    }
+
+    macro_rules! postgres_test {
+        ($name:ident) => {
+            #[tokio::test]
+            #[ignore = "Requires Docker - run with `cargo test -- --ignored`"]
+            async fn $name() {
+                let (repo, user_id) = create_repo().await;
+                super::$name(&repo, user_id).await;
+            }
+        };
+    }
+
+    postgres_test!(enrollment_lifecycle);
+    postgres_test!(record_use_rejects_replayed_steps);
+    postgres_test!(upsert_pending_resets_verification);
+}
@@ -0,0 +1,319 @@
+//! Shared tests for AgentRepo implementations
+
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    db::{error::DbError, repos::AgentRepo},
+    models::{AgentRunOutcome, AgentRunStatus, CreateAgent, CreateAgentRun, UpdateAgent},
+};
+
+fn create_agent_input(name: &str) -> CreateAgent {
+    CreateAgent {
+        name: name.to_string(),
+        description: Some("Answers support questions".to_string()),
+        model: "openai/gpt-4o".to_string(),
+        instructions: Some("Be concise.".to_string()),
+        tools: Vec::new(),
+        vector_store_ids: vec!["vs_123".to_string()],
+        max_iterations: Some(5),
+    }
+}
+
+fn create_run_input(agent_id: Uuid, org_id: Uuid, project_id: Option<Uuid>) -> CreateAgentRun {
+    CreateAgentRun {
+        agent_id,
+        org_id,
+        project_id,
+        user_id: Some(Uuid::new_v4()),
+        api_key_id: None,
+        service_account_id: None,
+        provider: "openai".to_string(),
+        model: "gpt-4o".to_string(),
+        input: json!("What is the refund policy?"),
+    }
+}
+
+pub async fn agent_crud_round_trips(repo: &dyn AgentRepo) {
+    let org_id = Uuid::new_v4();
+    let created = repo
+        .create_agent(org_id, create_agent_input("support"))
+        .await
+        .expect("create agent");
+    assert_eq!(created.org_id, org_id);
+    assert_eq!(created.vector_store_ids, vec!["vs_123".to_string()]);
+
+    let fetched = repo
+        .get_agent(created.id)
+        .await
+        .expect("get agent")
+        .expect("agent exists");
+    assert_eq!(fetched.name, "support");
+    assert_eq!(fetched.max_iterations, Some(5));
+
+    repo.create_agent(org_id, create_agent_input("billing"))
+        .await
+        .expect("create second agent");
+    repo.create_agent(Uuid::new_v4(), create_agent_input("support"))
+        .await
+        .expect("same name in another org");
+    let names: Vec<_> = repo
+        .list_agents(org_id)
+        .await
+        .expect("list agents")
+        .into_iter()
+        .map(|a| a.name)
+        .collect();
+    assert_eq!(names, vec!["billing", "support"]);
+
+    let updated = repo
+        .update_agent(
+            created.id,
+            UpdateAgent {
+                instructions: Some("Cite your sources.".to_string()),
+                max_iterations: Some(3),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("update agent");
+    assert_eq!(updated.instructions.as_deref(), Some("Cite your sources."));
+    assert_eq!(updated.max_iterations, Some(3));
+    assert_eq!(updated.name, "support");
+    assert_eq!(updated.model, "openai/gpt-4o");
+
+    repo.delete_agent(created.id).await.expect("delete agent");
+    assert!(
+        repo.get_agent(created.id)
+            .await
+            .expect("get deleted agent")
+            .is_none()
+    );
+}
+
+pub async fn agent_names_are_unique_per_org(repo: &dyn AgentRepo) {
+    let org_id = Uuid::new_v4();
+    repo.create_agent(org_id, create_agent_input("support"))
+        .await
+        .expect("create agent");
+    let other = repo
+        .create_agent(org_id, create_agent_input("billing"))
+        .await
+        .expect("create second agent");
+
+    let err = repo
+        .create_agent(org_id, create_agent_input("support"))
+        .await
+        .expect_err("duplicate name should fail");
+    assert!(matches!(err, DbError::Conflict(_)));
+
+    let err = repo
+        .update_agent(
+            other.id,
+            UpdateAgent {
+                name: Some("support".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect_err("rename to an existing name should fail");
+    assert!(matches!(err, DbError::Conflict(_)));
+}
+
+pub async fn missing_agent_is_not_found(repo: &dyn AgentRepo) {
+    let err = repo
+        .update_agent(Uuid::new_v4(), UpdateAgent::default())
+        .await
+        .expect_err("update missing agent");
+    assert!(matches!(err, DbError::NotFound));
+
+    let err = repo
+        .delete_agent(Uuid::new_v4())
+        .await
+        .expect_err("delete missing agent");
+    assert!(matches!(err, DbError::NotFound));
+}
+
+pub async fn runs_are_listed_newest_first_by_project(repo: &dyn AgentRepo) {
+    let org_id = Uuid::new_v4();
+    let project_id = Uuid::new_v4();
+    let agent = repo
+        .create_agent(org_id, create_agent_input("support"))
+        .await
+        .expect("create agent");
+
+    let org_run = repo
+        .create_run(create_run_input(agent.id, org_id, None))
+        .await
+        .expect("create org run");
+    assert_eq!(org_run.status, AgentRunStatus::InProgress);
+    assert!(org_run.completed_at.is_none());
+    let project_run = repo
+        .create_run(create_run_input(agent.id, org_id, Some(project_id)))
+        .await
+        .expect("create project run");
+
+    let fetched = repo
+        .get_run(org_run.id)
+        .await
+        .expect("get run")
+        .expect("run exists");
+    assert_eq!(fetched, org_run);
+
+    let runs = repo.list_runs(agent.id, None, 10).await.expect("list runs");
+    assert_eq!(runs.len(), 2);
+
+    let project_runs = repo
+        .list_runs(agent.id, Some(project_id), 10)
+        .await
+        .expect("list project runs");
+    assert_eq!(project_runs.len(), 1);
+    assert_eq!(project_runs[0].id, project_run.id);
+
+    assert_eq!(
+        repo.list_runs(agent.id, None, 1)
+            .await
+            .expect("list limited")
+            .len(),
+        1
+    );
+}
+
+pub async fn steps_are_recorded_in_order(repo: &dyn AgentRepo) {
+    let org_id = Uuid::new_v4();
+    let agent = repo
+        .create_agent(org_id, create_agent_input("support"))
+        .await
+        .expect("create agent");
+    let run = repo
+        .create_run(create_run_input(agent.id, org_id, None))
+        .await
+        .expect("create run");
+
+    let call = json!({"type": "file_search_call", "id": "fs_1", "queries": ["refunds"]});
+    let message = json!({"type": "message", "id": "msg_1", "content": []});
+    repo.add_step(run.id, 1, "message", &message)
+        .await
+        .expect("add message step");
+    repo.add_step(run.id, 0, "file_search_call", &call)
+        .await
+        .expect("add call step");
+
+    let err = repo
+        .add_step(run.id, 1, "message", &message)
+        .await
+        .expect_err("duplicate sequence should fail");
+    assert!(matches!(err, DbError::Conflict(_)));
+
+    let steps = repo.list_steps(run.id).await.expect("list steps");
+    assert_eq!(steps.len(), 2);
+    assert_eq!(steps[0].step_type, "file_search_call");
+    assert_eq!(steps[0].content, call);
+    assert_eq!(steps[1].sequence, 1);
+
+    // Deleting the agent takes its runs and steps with it
+    repo.delete_agent(agent.id).await.expect("delete agent");
+    assert!(repo.get_run(run.id).await.expect("get run").is_none());
+    assert!(
+        repo.list_steps(run.id)
+            .await
+            .expect("list steps")
+            .is_empty()
+    );
+}
+
+pub async fn finish_run_stores_outcome(repo: &dyn AgentRepo) {
+    let org_id = Uuid::new_v4();
+    let agent = repo
+        .create_agent(org_id, create_agent_input("support"))
+        .await
+        .expect("create agent");
+    let run = repo
+        .create_run(create_run_input(agent.id, org_id, None))
+        .await
+        .expect("create run");
+
+    let outcome = AgentRunOutcome {
+        status: AgentRunStatus::Completed,
+        output_text: Some("Refunds are accepted within 30 days.".to_string()),
+        error: None,
+        input_tokens: 1200,
+        output_tokens: 80,
+        cost_microcents: Some(4_500),
+    };
+    let finished = repo.finish_run(run.id, &outcome).await.expect("finish run");
+    assert_eq!(finished.status, AgentRunStatus::Completed);
+    assert_eq!(finished.input_tokens, 1200);
+    assert_eq!(finished.cost_microcents, Some(4_500));
+    assert!(finished.completed_at.is_some());
+    assert_eq!(finished.created_at, run.created_at);
+
+    let err = repo
+        .finish_run(Uuid::new_v4(), &outcome)
+        .await
+        .expect_err("missing run should fail");
+    assert!(matches!(err, DbError::NotFound));
+}
+
+#[cfg(all(test, feature = "database-sqlite"))]
+mod sqlite_tests {
+    use crate::db::{
+        sqlite::SqliteAgentRepo,
+        tests::harness::{create_sqlite_pool, run_sqlite_migrations},
+    };
+
+    async fn create_repo() -> SqliteAgentRepo {
+        let pool = create_sqlite_pool().await;
+        run_sqlite_migrations(&pool).await;
+        SqliteAgentRepo::new(pool)
+    }
+
+    macro_rules! sqlite_test {
+        ($name:ident) => {
+            #[tokio::test]
+            async fn $name() {
+                let repo = create_repo().await;
+                super::$name(&repo).await;
+            }
+        };
+    }
+
+    sqlite_test!(agent_crud_round_trips);
+    sqlite_test!(agent_names_are_unique_per_org);
+    sqlite_test!(missing_agent_is_not_found);
+    sqlite_test!(runs_are_listed_newest_first_by_project);
+    sqlite_test!(steps_are_recorded_in_order);
+    sqlite_test!(finish_run_stores_outcome);
+}
+
+#[cfg(all(test, feature = "database-postgres"))]
+mod postgres_tests {
+    use crate::db::{
+        postgres::PostgresAgentRepo,
+        tests::harness::postgres::{create_isolated_postgres_pool, run_postgres_migrations},
+    };
+
+    async fn create_repo() -> PostgresAgentRepo {
+        let pool = create_isolated_postgres_pool().await;
+        run_postgres_migrations(&pool).await;
+        PostgresAgentRepo::new(pool, None)
+    }
+
+    macro_rules! postgres_test {
+        ($name:ident) => {
+            #[tokio::test]
+            #[ignore = "Requires Docker - run with `cargo test -- --ignored`"]
+            async fn $name() {
+                let repo = create_repo().await;
+                super::$name(&repo).await;
+            }
+        };
+    }
+
+    postgres_test!(agent_crud_round_trips);
+    postgres_test!(agent_names_are_unique_per_org);
+    postgres_test!(missing_agent_is_not_found);
+    postgres_test!(runs_are_listed_newest_first_by_project);
+    postgres_test!(steps_are_recorded_in_order);
+    postgres_test!(finish_run_stores_outcome);
+}
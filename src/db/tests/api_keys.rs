@@ -3,15 +3,21 @@
 //! Tests are written as async functions that take a test context containing
 //! the api key repo and utilities for creating test organizations and projects.
 
+use std::collections::BTreeMap;
+
 use chrono::Utc;
 use uuid::Uuid;
 
 use crate::{
+    config::HashAlgorithm,
     db::{
         error::DbError,
         repos::{ApiKeyRepo, ListParams, OrganizationRepo, ProjectRepo},
     },
-    models::{ApiKeyOwner, BudgetPeriod, CreateApiKey, CreateOrganization, CreateProject},
+    models::{
+        ApiKeyHash, ApiKeyOwner, BudgetPeriod, CreateApiKey, CreateOrganization, CreateProject,
+        LabelSelector,
+    },
 };
 
 // ============================================================================
@@ -485,6 +491,58 @@ pub async fn test_list_by_org_pagination(ctx: &ApiKeyTestContext<'_>) {
     assert_ne!(page1.items[0].id, page2.items[0].id);
 }
 
+pub async fn test_list_by_org_label_selector(ctx: &ApiKeyTestContext<'_>) {
+    let org_id = ctx.create_test_org("test-org").await;
+
+    let mut keys = Vec::new();
+    for i in 0..3 {
+        keys.push(
+            ctx.api_key_repo
+                .create(
+                    create_org_api_key(&format!("Key {}", i), org_id),
+                    &format!("hash{:016}", i),
+                )
+                .await
+                .expect("Failed to create key"),
+        );
+    }
+    let labels = BTreeMap::from([("env".to_string(), "ci".to_string())]);
+    let updated = ctx
+        .api_key_repo
+        .set_labels(keys[1].id, &labels)
+        .await
+        .expect("Failed to set labels");
+    assert_eq!(updated.labels, labels);
+
+    let result = ctx
+        .api_key_repo
+        .list_by_org(
+            org_id,
+            ListParams {
+                labels: LabelSelector::parse("env=ci").unwrap(),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to list keys");
+    assert_eq!(result.items.len(), 1);
+    assert_eq!(result.items[0].id, keys[1].id);
+    assert_eq!(result.items[0].labels, labels);
+
+    let result = ctx
+        .api_key_repo
+        .list_by_org(
+            org_id,
+            ListParams {
+                labels: LabelSelector::parse("env=prod").unwrap(),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to list keys");
+    assert!(result.items.is_empty());
+}
+
 pub async fn test_count_by_org(ctx: &ApiKeyTestContext<'_>) {
     let org_id = ctx.create_test_org("test-org").await;
 
@@ -507,6 +565,58 @@ pub async fn test_count_by_org(ctx: &ApiKeyTestContext<'_>) {
     assert_eq!(count, 3);
 }
 
+pub async fn test_count_active_by_org(ctx: &ApiKeyTestContext<'_>) {
+    let org_id = ctx.create_test_org("test-org").await;
+    let project_id = ctx.create_test_project(org_id, "test-project").await;
+    let other_org_id = ctx.create_test_org("other-org").await;
+
+    ctx.api_key_repo
+        .create(create_org_api_key("Org Key", org_id), "activeorg0000001")
+        .await
+        .expect("Failed to create key");
+    ctx.api_key_repo
+        .create(
+            create_project_api_key("Project Key", project_id),
+            "activeorg0000002",
+        )
+        .await
+        .expect("Failed to create key");
+    let revoked = ctx
+        .api_key_repo
+        .create(
+            create_org_api_key("Revoked Key", org_id),
+            "activeorg0000003",
+        )
+        .await
+        .expect("Failed to create key");
+    ctx.api_key_repo
+        .revoke(revoked.id)
+        .await
+        .expect("Failed to revoke key");
+    ctx.api_key_repo
+        .create(
+            create_user_api_key("User Key", Uuid::new_v4()),
+            "activeorg0000004",
+        )
+        .await
+        .expect("Failed to create key");
+    ctx.api_key_repo
+        .create(
+            create_org_api_key("Other Org Key", other_org_id),
+            "activeorg0000005",
+        )
+        .await
+        .expect("Failed to create key");
+
+    // Org and project keys count; revoked, user and other orgs' keys don't
+    let count = ctx
+        .api_key_repo
+        .count_active_by_org(org_id)
+        .await
+        .expect("Failed to count keys");
+    assert_eq!(count, 2);
+}
+
 // ============================================================================
 // List By Project Tests
 // ============================================================================
@@ -878,6 +988,44 @@ pub async fn test_rotate_creates_new_key(ctx: &ApiKeyTestContext<'_>) {
     assert!(matches!(new_key.owner, ApiKeyOwner::Organization { org_id: id } if id == org_id));
 }
 
+pub async fn test_rotate_keeps_labels(ctx: &ApiKeyTestContext<'_>) {
+    let org_id = ctx.create_test_org("test-org").await;
+    let old_key = ctx
+        .api_key_repo
+        .create(
+            create_org_api_key("Labelled Key", org_id),
+            "labelledhash1234",
+        )
+        .await
+        .expect("Failed to create old key");
+    let labels = BTreeMap::from([("owner".to_string(), "billing".to_string())]);
+    ctx.api_key_repo
+        .set_labels(old_key.id, &labels)
+        .await
+        .expect("Failed to set labels");
+
+    let grace_until = Utc::now() + chrono::Duration::hours(24);
+    let new_key = ctx
+        .api_key_repo
+        .rotate(
+            old_key.id,
+            create_org_api_key("Labelled Key (rotated)", org_id),
+            "labelledhash5678",
+            grace_until,
+        )
+        .await
+        .expect("Failed to rotate key");
+
+    assert_eq!(new_key.labels, labels);
+    let fetched = ctx
+        .api_key_repo
+        .get_by_id(new_key.id)
+        .await
+        .expect("Failed to get key")
+        .expect("Key should exist");
+    assert_eq!(fetched.labels, labels);
+}
+
 pub async fn test_rotate_sets_grace_until_on_old_key(ctx: &ApiKeyTestContext<'_>) {
     let org_id = ctx.create_test_org("test-org").await;
     let old_key = ctx
@@ -1085,6 +1233,91 @@ pub async fn test_get_key_hashes_by_user_empty(ctx: &ApiKeyTestContext<'_>) {
     assert!(hashes.is_empty());
 }
 
+// ============================================================================
+// Hash Scheme Tests
+// ============================================================================
+
+pub async fn test_get_by_hash_reports_hash_scheme(ctx: &ApiKeyTestContext<'_>) {
+    let org_id = ctx.create_test_org("test-org").await;
+    ctx.api_key_repo
+        .create(create_org_api_key("Legacy", org_id), "legacyhash_12345")
+        .await
+        .expect("Failed to create key");
+    let argon2 = ApiKeyHash {
+        key_hash: "argon2lookup_123".to_string(),
+        scheme: HashAlgorithm::Argon2,
+        verifier: Some("$argon2id$v=19$m=19456,t=2,p=1$c2FsdA$aGFzaA".to_string()),
+    };
+    ctx.api_key_repo
+        .create_with_hash(create_org_api_key("Argon2", org_id), &argon2)
+        .await
+        .expect("Failed to create key");
+
+    let legacy = ctx
+        .api_key_repo
+        .get_by_hash("legacyhash_12345")
+        .await
+        .expect("Query should succeed")
+        .expect("Key should exist");
+    assert_eq!(legacy.hash_scheme, "sha256");
+    assert!(legacy.key_verifier.is_none());
+
+    let fetched = ctx
+        .api_key_repo
+        .get_by_hash("argon2lookup_123")
+        .await
+        .expect("Query should succeed")
+        .expect("Key should exist");
+    assert_eq!(fetched.hash_scheme, "argon2");
+    assert_eq!(fetched.key_verifier, argon2.verifier);
+}
+
+pub async fn test_update_hash(ctx: &ApiKeyTestContext<'_>) {
+    let org_id = ctx.create_test_org("test-org").await;
+    let created = ctx
+        .api_key_repo
+        .create(create_org_api_key("Rehash", org_id), "legacyhash_12345")
+        .await
+        .expect("Failed to create key");
+    let rehashed = ApiKeyHash {
+        key_hash: "hmachash_1234567".to_string(),
+        scheme: HashAlgorithm::HmacSha256,
+        verifier: None,
+    };
+
+    assert!(
+        ctx.api_key_repo
+            .update_hash(created.id, "legacyhash_12345", &rehashed)
+            .await
+            .expect("Failed to update hash")
+    );
+    // A second re-hash from the stale hash is a no-op
+    assert!(
+        !ctx.api_key_repo
+            .update_hash(created.id, "legacyhash_12345", &rehashed)
+            .await
+            .expect("Failed to update hash")
+    );
+
+    assert!(
+        ctx.api_key_repo
+            .get_by_hash("legacyhash_12345")
+            .await
+            .expect("Query should succeed")
+            .is_none()
+    );
+    let fetched = ctx
+        .api_key_repo
+        .get_by_hash("hmachash_1234567")
+        .await
+        .expect("Query should succeed")
+        .expect("Key should exist");
+    assert_eq!(fetched.key.id, created.id);
+    assert_eq!(fetched.hash_scheme, "hmac_sha256");
+    // The displayed prefix doesn't change
+    assert_eq!(fetched.key.key_prefix, created.key_prefix);
+}
+
 // ============================================================================
 // SQLite Tests - Fast, in-memory
 // ============================================================================
@@ -1146,7 +1379,9 @@ mod sqlite_tests {
     sqlite_test!(test_list_by_org);
     sqlite_test!(test_list_by_org_only_returns_org_keys);
     sqlite_test!(test_list_by_org_pagination);
+    sqlite_test!(test_list_by_org_label_selector);
     sqlite_test!(test_count_by_org);
+    sqlite_test!(test_count_active_by_org);
 
     // List by project tests
     sqlite_test!(test_list_by_project);
@@ -1175,6 +1410,7 @@ mod sqlite_tests {
 
     // Rotation tests
     sqlite_test!(test_rotate_creates_new_key);
+    sqlite_test!(test_rotate_keeps_labels);
     sqlite_test!(test_rotate_sets_grace_until_on_old_key);
     sqlite_test!(test_old_key_works_during_grace_period);
     sqlite_test!(test_old_key_fails_after_grace_period);
@@ -1183,6 +1419,10 @@ mod sqlite_tests {
     // Get key hashes by user tests
     sqlite_test!(test_get_key_hashes_by_user);
     sqlite_test!(test_get_key_hashes_by_user_empty);
+
+    // Hash scheme tests
+    sqlite_test!(test_get_by_hash_reports_hash_scheme);
+    sqlite_test!(test_update_hash);
 }
 
 // ============================================================================
@@ -1241,7 +1481,9 @@ mod postgres_tests {
     postgres_test!(test_list_by_org);
     postgres_test!(test_list_by_org_only_returns_org_keys);
     postgres_test!(test_list_by_org_pagination);
+    postgres_test!(test_list_by_org_label_selector);
     postgres_test!(test_count_by_org);
+    postgres_test!(test_count_active_by_org);
 
     // List by project tests
     postgres_test!(test_list_by_project);
@@ -1270,6 +1512,7 @@ mod postgres_tests {
 
     // Rotation tests
     postgres_test!(test_rotate_creates_new_key);
+    postgres_test!(test_rotate_keeps_labels);
     postgres_test!(test_rotate_sets_grace_until_on_old_key);
     postgres_test!(test_old_key_works_during_grace_period);
     postgres_test!(test_old_key_fails_after_grace_period);
@@ -1278,4 +1521,8 @@ mod postgres_tests {
     // Get key hashes by user tests
     postgres_test!(test_get_key_hashes_by_user);
     postgres_test!(test_get_key_hashes_by_user_empty);
+
+    // Hash scheme tests
+    postgres_test!(test_get_by_hash_reports_hash_scheme);
+    postgres_test!(test_update_hash);
 }
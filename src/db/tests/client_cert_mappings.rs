@@ -0,0 +1,303 @@
+//! Shared tests for ClientCertMappingRepo implementations
+
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        error::DbError,
+        repos::{ClientCertMappingRepo, ListParams, ServiceAccountRepo},
+    },
+    models::{CertMatchType, CreateClientCertMapping, CreateServiceAccount},
+};
+
+async fn create_service_account(repo: &dyn ServiceAccountRepo, org_id: Uuid, slug: &str) -> Uuid {
+    repo.create(
+        org_id,
+        CreateServiceAccount {
+            slug: slug.to_string(),
+            name: slug.to_string(),
+            description: None,
+            roles: vec!["developer".to_string()],
+        },
+    )
+    .await
+    .expect("create service account")
+    .id
+}
+
+fn mapping(match_type: CertMatchType, value: &str) -> CreateClientCertMapping {
+    CreateClientCertMapping {
+        match_type,
+        match_value: value.to_string(),
+        description: Some("test".to_string()),
+    }
+}
+
+pub async fn create_get_and_delete(
+    repo: &dyn ClientCertMappingRepo,
+    sa_repo: &dyn ServiceAccountRepo,
+    org_id: Uuid,
+) {
+    let sa_id = create_service_account(sa_repo, org_id, "billing").await;
+    let created = repo
+        .create(
+            org_id,
+            sa_id,
+            mapping(CertMatchType::SanUri, "spiffe://example.org/billing"),
+        )
+        .await
+        .expect("create mapping");
+
+    let fetched = repo
+        .get_by_id(created.id)
+        .await
+        .expect("get mapping")
+        .expect("mapping exists");
+    assert_eq!(fetched.org_id, org_id);
+    assert_eq!(fetched.service_account_id, sa_id);
+    assert_eq!(fetched.match_type, CertMatchType::SanUri);
+    assert_eq!(fetched.match_value, "spiffe://example.org/billing");
+    assert_eq!(fetched.created_at, created.created_at);
+
+    repo.delete(created.id).await.expect("delete mapping");
+    assert!(repo.get_by_id(created.id).await.unwrap().is_none());
+    assert!(matches!(
+        repo.delete(created.id).await,
+        Err(DbError::NotFound)
+    ));
+}
+
+pub async fn duplicate_identifier_conflicts(
+    repo: &dyn ClientCertMappingRepo,
+    sa_repo: &dyn ServiceAccountRepo,
+    org_id: Uuid,
+) {
+    let first = create_service_account(sa_repo, org_id, "first").await;
+    let second = create_service_account(sa_repo, org_id, "second").await;
+
+    repo.create(
+        org_id,
+        first,
+        mapping(CertMatchType::SanDns, "svc.internal"),
+    )
+    .await
+    .expect("create mapping");
+    let err = repo
+        .create(
+            org_id,
+            second,
+            mapping(CertMatchType::SanDns, "svc.internal"),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DbError::Conflict(_)), "got {err:?}");
+
+    // Same value under a different match type is a different identifier
+    repo.create(
+        org_id,
+        second,
+        mapping(CertMatchType::SubjectCn, "svc.internal"),
+    )
+    .await
+    .expect("create CN mapping");
+}
+
+pub async fn find_by_identifiers_matches_type_and_value(
+    repo: &dyn ClientCertMappingRepo,
+    sa_repo: &dyn ServiceAccountRepo,
+    org_id: Uuid,
+) {
+    let sa_id = create_service_account(sa_repo, org_id, "ingest").await;
+    repo.create(org_id, sa_id, mapping(CertMatchType::SubjectCn, "ingest"))
+        .await
+        .unwrap();
+    repo.create(
+        org_id,
+        sa_id,
+        mapping(CertMatchType::SanDns, "ingest.internal"),
+    )
+    .await
+    .unwrap();
+
+    assert!(repo.find_by_identifiers(&[]).await.unwrap().is_empty());
+
+    let found = repo
+        .find_by_identifiers(&[
+            (CertMatchType::SanDns, "ingest.internal".to_string()),
+            (CertMatchType::SubjectCn, "ingest".to_string()),
+            (CertMatchType::SanUri, "spiffe://unmapped".to_string()),
+        ])
+        .await
+        .expect("find mappings");
+    assert_eq!(found.len(), 2);
+    assert!(found.iter().all(|m| m.service_account_id == sa_id));
+
+    // A value only matches under the field it was mapped for
+    let found = repo
+        .find_by_identifiers(&[(CertMatchType::SanDns, "ingest".to_string())])
+        .await
+        .unwrap();
+    assert!(found.is_empty());
+
+    // Deleted service accounts no longer authenticate
+    sa_repo.delete(sa_id).await.expect("delete service account");
+    let found = repo
+        .find_by_identifiers(&[(CertMatchType::SubjectCn, "ingest".to_string())])
+        .await
+        .unwrap();
+    assert!(found.is_empty());
+}
+
+pub async fn list_by_service_account_paginates(
+    repo: &dyn ClientCertMappingRepo,
+    sa_repo: &dyn ServiceAccountRepo,
+    org_id: Uuid,
+) {
+    let sa_id = create_service_account(sa_repo, org_id, "worker").await;
+    let other = create_service_account(sa_repo, org_id, "other").await;
+    for i in 0..3 {
+        repo.create(
+            org_id,
+            sa_id,
+            mapping(CertMatchType::SanDns, &format!("worker-{i}.internal")),
+        )
+        .await
+        .unwrap();
+    }
+    repo.create(
+        org_id,
+        other,
+        mapping(CertMatchType::SanDns, "other.internal"),
+    )
+    .await
+    .unwrap();
+
+    let page = repo
+        .list_by_service_account(
+            sa_id,
+            ListParams {
+                limit: Some(2),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("list first page");
+    assert_eq!(page.items.len(), 2);
+    assert!(page.has_more);
+
+    let next = repo
+        .list_by_service_account(
+            sa_id,
+            ListParams {
+                limit: Some(2),
+                cursor: page.cursors.next.clone(),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("list second page");
+    assert_eq!(next.items.len(), 1);
+    assert!(!next.has_more);
+    assert!(next.items.iter().all(|m| m.service_account_id == sa_id));
+}
+
+#[cfg(all(test, feature = "database-sqlite"))]
+mod sqlite_tests {
+    use uuid::Uuid;
+
+    use crate::{
+        db::{
+            repos::OrganizationRepo,
+            sqlite::{
+                SqliteClientCertMappingRepo, SqliteOrganizationRepo, SqliteServiceAccountRepo,
+            },
+            tests::harness::{create_sqlite_pool, run_sqlite_migrations},
+        },
+        models::CreateOrganization,
+    };
+
+    async fn create_repos() -> (SqliteClientCertMappingRepo, SqliteServiceAccountRepo, Uuid) {
+        let pool = create_sqlite_pool().await;
+        run_sqlite_migrations(&pool).await;
+        let org = SqliteOrganizationRepo::new(pool.clone())
+            .create(CreateOrganization {
+                slug: "acme".to_string(),
+                name: "Acme".to_string(),
+            })
+            .await
+            .expect("create org");
+        (
+            SqliteClientCertMappingRepo::new(pool.clone()),
+            SqliteServiceAccountRepo::new(pool),
+            org.id,
+        )
+    }
+
+    macro_rules! sqlite_test {
+        ($name:ident) => {
+            #[tokio::test]
+            async fn $name() {
+                let (repo, sa_repo, org_id) = create_repos().await;
+                super::$name(&repo, &sa_repo, org_id).await;
+            }
+        };
+    }
+
+    sqlite_test!(create_get_and_delete);
+    sqlite_test!(duplicate_identifier_conflicts);
+    sqlite_test!(find_by_identifiers_matches_type_and_value);
+    sqlite_test!(list_by_service_account_paginates);
+}
+
+#[cfg(all(test, feature = "database-postgres"))]
+mod postgres_tests {
+    use uuid::Uuid;
+
+    use crate::{
+        db::{
+            postgres::{
+                PostgresClientCertMappingRepo, PostgresOrganizationRepo, PostgresServiceAccountRepo,
+            },
+            repos::OrganizationRepo,
+            tests::harness::postgres::{create_isolated_postgres_pool, run_postgres_migrations},
+        },
+        models::CreateOrganization,
+    };
+
+    async fn create_repos() -> (
+        PostgresClientCertMappingRepo,
+        PostgresServiceAccountRepo,
+        Uuid,
+    ) {
+        let pool = create_isolated_postgres_pool().await;
+        run_postgres_migrations(&pool).await;
+        let org = PostgresOrganizationRepo::new(pool.clone(), None)
+            .create(CreateOrganization {
+                slug: "acme".to_string(),
+                name: "Acme".to_string(),
+            })
+            .await
+            .expect("create org");
+        (
+            PostgresClientCertMappingRepo::new(pool.clone(), None),
+            PostgresServiceAccountRepo::new(pool, None),
+            org.id,
+        )
+    }
+
+    macro_rules! postgres_test {
+        ($name:ident) => {
+            #[tokio::test]
+            #[ignore = "Requires Docker - run with `cargo test -- --ignored`"]
+            async fn $name() {
+                let (repo, sa_repo, org_id) = create_repos().await;
+                super::$name(&repo, &sa_repo, org_id).await;
+            }
+        };
+    }
+
+    postgres_test!(create_get_and_delete);
+    postgres_test!(duplicate_identifier_conflicts);
+    postgres_test!(find_by_identifiers_matches_type_and_value);
+    postgres_test!(list_by_service_account_paginates);
+}
@@ -11,8 +11,8 @@ use crate::{
         repos::{ConversationRepo, ListParams},
     },
     models::{
-        AppendMessages, ConversationOwner, ConversationOwnerType, CreateConversation, Message,
-        UpdateConversation,
+        AppendMessages, ConversationOwner, ConversationOwnerType, CreateConversation,
+        ForkConversation, Message, SetConversationSummary, UpdateConversation,
     },
 };
 
@@ -24,6 +24,8 @@ fn create_message(role: &str, content: &str) -> Message {
     Message {
         role: role.to_string(),
         content: content.to_string(),
+        annotations: Vec::new(),
+        attachments: Vec::new(),
     }
 }
 
@@ -731,6 +733,43 @@ pub async fn test_append_messages_to_existing(repo: &dyn ConversationRepo) {
     assert_eq!(messages[2].content, "Third");
 }
 
+pub async fn test_append_messages_preserves_annotations(repo: &dyn ConversationRepo) {
+    let user_id = Uuid::new_v4();
+    let input =
+        create_conversation_input(ConversationOwner::User { user_id }, "Test", vec![], vec![]);
+    let created = repo.create(input).await.expect("Failed to create");
+
+    let citation = serde_json::json!({
+        "type": "file_citation",
+        "file_id": Uuid::new_v4().to_string(),
+        "filename": "report.pdf",
+        "index": 13,
+        "chunk_start": 1840,
+        "chunk_end": 2912,
+        "score": 0.87
+    });
+    let mut answer = create_message("assistant", "Revenue grew [Source 1].");
+    answer.annotations = vec![citation.clone()];
+
+    repo.append_messages(
+        created.id,
+        AppendMessages {
+            messages: vec![create_message("user", "How did revenue do?"), answer],
+        },
+    )
+    .await
+    .expect("Failed to append");
+
+    let fetched = repo
+        .get_by_id(created.id)
+        .await
+        .expect("Failed to get")
+        .expect("Should exist");
+
+    assert!(fetched.messages[0].annotations.is_empty());
+    assert_eq!(fetched.messages[1].annotations, vec![citation]);
+}
+
 pub async fn test_append_messages_not_found(repo: &dyn ConversationRepo) {
     let result = repo
         .append_messages(
@@ -919,6 +958,433 @@ pub async fn test_update_to_empty_models(repo: &dyn ConversationRepo) {
     assert!(updated.models.is_empty());
 }
 
+// ============================================================================
+// Branching Tests
+// ============================================================================
+
+fn three_message_conversation(title: &str) -> CreateConversation {
+    create_conversation_input(
+        ConversationOwner::User {
+            user_id: Uuid::new_v4(),
+        },
+        title,
+        vec!["gpt-4"],
+        vec![
+            create_message("user", "Hello"),
+            create_message("assistant", "Hi there"),
+            create_message("user", "Tell me a joke"),
+        ],
+    )
+}
+
+pub async fn test_list_branches_empty_before_fork(repo: &dyn ConversationRepo) {
+    let conv = repo
+        .create(three_message_conversation("Unforked"))
+        .await
+        .expect("Failed to create");
+
+    let branches = repo.list_branches(conv.id).await.expect("Failed to list");
+    assert!(branches.is_empty());
+}
+
+pub async fn test_fork_edit_creates_root_and_branch(repo: &dyn ConversationRepo) {
+    let conv = repo
+        .create(three_message_conversation("Edit"))
+        .await
+        .expect("Failed to create");
+
+    let branch = repo
+        .fork(
+            conv.id,
+            ForkConversation {
+                message_index: 2,
+                message: Some(create_message("user", "Tell me a story")),
+            },
+        )
+        .await
+        .expect("Failed to fork");
+
+    assert!(branch.is_active);
+    assert_eq!(branch.fork_index, 2);
+    assert_eq!(branch.messages.len(), 3);
+    assert_eq!(branch.messages[1].content, "Hi there");
+    assert_eq!(branch.messages[2].content, "Tell me a story");
+
+    // The pre-fork history is kept as the inactive root branch
+    let branches = repo.list_branches(conv.id).await.expect("Failed to list");
+    assert_eq!(branches.len(), 2);
+    let root = &branches[0];
+    assert!(root.parent_branch_id.is_none());
+    assert!(!root.is_active);
+    assert_eq!(root.messages[2].content, "Tell me a joke");
+    assert_eq!(branches[1].id, branch.id);
+    assert_eq!(branch.parent_branch_id, Some(root.id));
+
+    // The conversation mirrors the active branch
+    let fetched = repo
+        .get_by_id(conv.id)
+        .await
+        .expect("Failed to get")
+        .unwrap();
+    assert_eq!(fetched.messages.len(), 3);
+    assert_eq!(fetched.messages[2].content, "Tell me a story");
+}
+
+pub async fn test_fork_delete_truncates(repo: &dyn ConversationRepo) {
+    let conv = repo
+        .create(three_message_conversation("Delete"))
+        .await
+        .expect("Failed to create");
+
+    let branch = repo
+        .fork(
+            conv.id,
+            ForkConversation {
+                message_index: 1,
+                message: None,
+            },
+        )
+        .await
+        .expect("Failed to fork");
+
+    assert_eq!(branch.messages.len(), 1);
+    assert_eq!(branch.messages[0].content, "Hello");
+
+    let fetched = repo
+        .get_by_id(conv.id)
+        .await
+        .expect("Failed to get")
+        .unwrap();
+    assert_eq!(fetched.messages.len(), 1);
+}
+
+pub async fn test_fork_from_branch_sets_parent(repo: &dyn ConversationRepo) {
+    let conv = repo
+        .create(three_message_conversation("Nested"))
+        .await
+        .expect("Failed to create");
+
+    let first = repo
+        .fork(
+            conv.id,
+            ForkConversation {
+                message_index: 2,
+                message: Some(create_message("user", "Second try")),
+            },
+        )
+        .await
+        .expect("Failed to fork");
+    let second = repo
+        .fork(
+            conv.id,
+            ForkConversation {
+                message_index: 2,
+                message: Some(create_message("user", "Third try")),
+            },
+        )
+        .await
+        .expect("Failed to fork");
+
+    assert_eq!(second.parent_branch_id, Some(first.id));
+
+    let branches = repo.list_branches(conv.id).await.expect("Failed to list");
+    assert_eq!(branches.len(), 3);
+    assert_eq!(branches.iter().filter(|b| b.is_active).count(), 1);
+    assert!(branches.iter().any(|b| b.id == second.id && b.is_active));
+}
+
+pub async fn test_fork_index_out_of_range(repo: &dyn ConversationRepo) {
+    let conv = repo
+        .create(three_message_conversation("Out of range"))
+        .await
+        .expect("Failed to create");
+
+    let result = repo
+        .fork(
+            conv.id,
+            ForkConversation {
+                message_index: 3,
+                message: None,
+            },
+        )
+        .await;
+    assert!(matches!(result, Err(DbError::Validation(_))));
+
+    // Nothing was recorded
+    let branches = repo.list_branches(conv.id).await.expect("Failed to list");
+    assert!(branches.is_empty());
+}
+
+pub async fn test_fork_not_found(repo: &dyn ConversationRepo) {
+    let result = repo
+        .fork(
+            Uuid::new_v4(),
+            ForkConversation {
+                message_index: 0,
+                message: None,
+            },
+        )
+        .await;
+    assert!(matches!(result, Err(DbError::NotFound)));
+}
+
+pub async fn test_append_messages_updates_active_branch(repo: &dyn ConversationRepo) {
+    let conv = repo
+        .create(three_message_conversation("Append"))
+        .await
+        .expect("Failed to create");
+
+    let branch = repo
+        .fork(
+            conv.id,
+            ForkConversation {
+                message_index: 2,
+                message: Some(create_message("user", "Tell me a story")),
+            },
+        )
+        .await
+        .expect("Failed to fork");
+
+    repo.append_messages(
+        conv.id,
+        AppendMessages {
+            messages: vec![create_message("assistant", "Once upon a time")],
+        },
+    )
+    .await
+    .expect("Failed to append");
+
+    let branches = repo.list_branches(conv.id).await.expect("Failed to list");
+    let active = branches.iter().find(|b| b.id == branch.id).unwrap();
+    assert_eq!(active.messages.len(), 4);
+    assert_eq!(active.messages[3].content, "Once upon a time");
+
+    // The root branch is untouched
+    assert_eq!(branches[0].messages.len(), 3);
+}
+
+pub async fn test_switch_branch(repo: &dyn ConversationRepo) {
+    let conv = repo
+        .create(three_message_conversation("Switch"))
+        .await
+        .expect("Failed to create");
+
+    repo.fork(
+        conv.id,
+        ForkConversation {
+            message_index: 2,
+            message: Some(create_message("user", "Tell me a story")),
+        },
+    )
+    .await
+    .expect("Failed to fork");
+
+    let root_id = repo.list_branches(conv.id).await.expect("Failed to list")[0].id;
+    let switched = repo
+        .switch_branch(conv.id, root_id)
+        .await
+        .expect("Failed to switch");
+
+    assert_eq!(switched.title, "Switch");
+    assert_eq!(switched.messages[2].content, "Tell me a joke");
+
+    let branches = repo.list_branches(conv.id).await.expect("Failed to list");
+    assert!(branches[0].is_active);
+    assert!(!branches[1].is_active);
+}
+
+pub async fn test_switch_branch_not_found(repo: &dyn ConversationRepo) {
+    let conv = repo
+        .create(three_message_conversation("Switch missing"))
+        .await
+        .expect("Failed to create");
+
+    let result = repo.switch_branch(conv.id, Uuid::new_v4()).await;
+    assert!(matches!(result, Err(DbError::NotFound)));
+}
+
+// ============================================================================
+// Summary Tests
+// ============================================================================
+
+fn summary_input(summary: &str, message_count: i32) -> SetConversationSummary {
+    SetConversationSummary {
+        summary: summary.to_string(),
+        message_count,
+        title: None,
+        expected_title: "New Chat".to_string(),
+    }
+}
+
+pub async fn test_list_needing_summary_personal(repo: &dyn ConversationRepo) {
+    let user_id = Uuid::new_v4();
+    let two_messages = vec![
+        create_message("user", "Hello"),
+        create_message("assistant", "Hi"),
+    ];
+    let eligible = repo
+        .create(create_conversation_input(
+            ConversationOwner::User { user_id },
+            "New Chat",
+            vec![],
+            two_messages.clone(),
+        ))
+        .await
+        .expect("Failed to create");
+    // Too short
+    repo.create(create_conversation_input(
+        ConversationOwner::User { user_id },
+        "New Chat",
+        vec![],
+        vec![create_message("user", "Hello")],
+    ))
+    .await
+    .expect("Failed to create");
+    // Project conversations are listed per organization
+    repo.create(create_conversation_input(
+        ConversationOwner::Project {
+            project_id: Uuid::new_v4(),
+        },
+        "New Chat",
+        vec![],
+        two_messages,
+    ))
+    .await
+    .expect("Failed to create");
+
+    let found = repo
+        .list_needing_summary(None, 2, 10, 10)
+        .await
+        .expect("Failed to list");
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].id, eligible.id);
+    assert!(found[0].summary.is_none());
+
+    let for_org = repo
+        .list_needing_summary(Some(Uuid::new_v4()), 2, 10, 10)
+        .await
+        .expect("Failed to list");
+    assert!(for_org.is_empty());
+}
+
+pub async fn test_list_needing_summary_after_new_messages(repo: &dyn ConversationRepo) {
+    let conv = repo
+        .create(three_message_conversation("New Chat"))
+        .await
+        .expect("Failed to create");
+    repo.set_summary(conv.id, summary_input("Summary", 3))
+        .await
+        .expect("Failed to set summary");
+
+    let found = repo.list_needing_summary(None, 2, 2, 10).await.unwrap();
+    assert!(found.is_empty(), "up-to-date summary should not be listed");
+
+    repo.append_messages(
+        conv.id,
+        AppendMessages {
+            messages: vec![create_message("user", "Another question")],
+        },
+    )
+    .await
+    .unwrap();
+    let found = repo.list_needing_summary(None, 2, 2, 10).await.unwrap();
+    assert!(found.is_empty(), "one new message is below the threshold");
+
+    repo.append_messages(
+        conv.id,
+        AppendMessages {
+            messages: vec![create_message("assistant", "Another answer")],
+        },
+    )
+    .await
+    .unwrap();
+    let found = repo.list_needing_summary(None, 2, 2, 10).await.unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].summary.as_deref(), Some("Summary"));
+}
+
+pub async fn test_list_needing_summary_after_truncation(repo: &dyn ConversationRepo) {
+    let conv = repo
+        .create(three_message_conversation("New Chat"))
+        .await
+        .expect("Failed to create");
+    repo.set_summary(conv.id, summary_input("Summary", 3))
+        .await
+        .expect("Failed to set summary");
+
+    repo.update(
+        conv.id,
+        UpdateConversation {
+            title: None,
+            models: None,
+            messages: Some(vec![
+                create_message("user", "Hello"),
+                create_message("assistant", "Hi"),
+            ]),
+            owner: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    let found = repo.list_needing_summary(None, 2, 10, 10).await.unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].id, conv.id);
+}
+
+pub async fn test_set_summary(repo: &dyn ConversationRepo) {
+    let conv = repo
+        .create(three_message_conversation("New Chat"))
+        .await
+        .expect("Failed to create");
+
+    let mut input = summary_input("They talked about Rust.", 3);
+    input.title = Some("Rust questions".to_string());
+    repo.set_summary(conv.id, input)
+        .await
+        .expect("Failed to set summary");
+
+    let fetched = repo.get_by_id(conv.id).await.unwrap().unwrap();
+    assert_eq!(fetched.title, "Rust questions");
+    assert_eq!(fetched.summary.as_deref(), Some("They talked about Rust."));
+    assert_eq!(fetched.updated_at, conv.updated_at);
+}
+
+pub async fn test_set_summary_keeps_renamed_title(repo: &dyn ConversationRepo) {
+    let conv = repo
+        .create(three_message_conversation("New Chat"))
+        .await
+        .expect("Failed to create");
+    repo.update(
+        conv.id,
+        UpdateConversation {
+            title: Some("My own title".to_string()),
+            models: None,
+            messages: None,
+            owner: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    let mut input = summary_input("Summary", 3);
+    input.title = Some("Generated".to_string());
+    repo.set_summary(conv.id, input)
+        .await
+        .expect("Failed to set summary");
+
+    let fetched = repo.get_by_id(conv.id).await.unwrap().unwrap();
+    assert_eq!(fetched.title, "My own title");
+    assert_eq!(fetched.summary.as_deref(), Some("Summary"));
+}
+
+pub async fn test_set_summary_not_found(repo: &dyn ConversationRepo) {
+    let result = repo
+        .set_summary(Uuid::new_v4(), summary_input("Summary", 1))
+        .await;
+    assert!(matches!(result, Err(DbError::NotFound)));
+}
+
 // ============================================================================
 // SQLite Tests - Fast, in-memory
 // ============================================================================
@@ -984,6 +1450,7 @@ mod sqlite_tests {
     // Append messages tests
     sqlite_test!(test_append_messages_to_empty);
     sqlite_test!(test_append_messages_to_existing);
+    sqlite_test!(test_append_messages_preserves_annotations);
     sqlite_test!(test_append_messages_not_found);
     sqlite_test!(test_append_messages_to_deleted_fails);
     sqlite_test!(test_append_messages_updates_timestamp);
@@ -998,6 +1465,25 @@ mod sqlite_tests {
     sqlite_test!(test_unicode_content);
     sqlite_test!(test_empty_models_vec);
     sqlite_test!(test_update_to_empty_models);
+
+    // Branching tests
+    sqlite_test!(test_list_branches_empty_before_fork);
+    sqlite_test!(test_fork_edit_creates_root_and_branch);
+    sqlite_test!(test_fork_delete_truncates);
+    sqlite_test!(test_fork_from_branch_sets_parent);
+    sqlite_test!(test_fork_index_out_of_range);
+    sqlite_test!(test_fork_not_found);
+    sqlite_test!(test_append_messages_updates_active_branch);
+    sqlite_test!(test_switch_branch);
+    sqlite_test!(test_switch_branch_not_found);
+
+    // Summary tests
+    sqlite_test!(test_list_needing_summary_personal);
+    sqlite_test!(test_list_needing_summary_after_new_messages);
+    sqlite_test!(test_list_needing_summary_after_truncation);
+    sqlite_test!(test_set_summary);
+    sqlite_test!(test_set_summary_keeps_renamed_title);
+    sqlite_test!(test_set_summary_not_found);
 }
 
 // ============================================================================
@@ -1062,6 +1548,7 @@ mod postgres_tests {
     // Append messages tests
     postgres_test!(test_append_messages_to_empty);
     postgres_test!(test_append_messages_to_existing);
+    postgres_test!(test_append_messages_preserves_annotations);
     postgres_test!(test_append_messages_not_found);
     postgres_test!(test_append_messages_to_deleted_fails);
     postgres_test!(test_append_messages_updates_timestamp);
@@ -1076,4 +1563,23 @@ mod postgres_tests {
     postgres_test!(test_unicode_content);
     postgres_test!(test_empty_models_vec);
     postgres_test!(test_update_to_empty_models);
+
+    // Branching tests
+    postgres_test!(test_list_branches_empty_before_fork);
+    postgres_test!(test_fork_edit_creates_root_and_branch);
+    postgres_test!(test_fork_delete_truncates);
+    postgres_test!(test_fork_from_branch_sets_parent);
+    postgres_test!(test_fork_index_out_of_range);
+    postgres_test!(test_fork_not_found);
+    postgres_test!(test_append_messages_updates_active_branch);
+    postgres_test!(test_switch_branch);
+    postgres_test!(test_switch_branch_not_found);
+
+    // Summary tests
+    postgres_test!(test_list_needing_summary_personal);
+    postgres_test!(test_list_needing_summary_after_new_messages);
+    postgres_test!(test_list_needing_summary_after_truncation);
+    postgres_test!(test_set_summary);
+    postgres_test!(test_set_summary_keeps_renamed_title);
+    postgres_test!(test_set_summary_not_found);
 }
@@ -0,0 +1,252 @@
+//! Shared tests for EmailLogRepo implementations
+
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::{
+    db::repos::{EmailLogRepo, ListParams},
+    models::{EmailKind, EmailLogFilter, EmailSendStatus, NewEmailLogEntry},
+};
+
+fn entry(org_id: Option<Uuid>, kind: EmailKind, status: EmailSendStatus) -> NewEmailLogEntry {
+    NewEmailLogEntry {
+        org_id,
+        kind,
+        from_address: "Hadrian <noreply@example.com>".to_string(),
+        recipients: vec![
+            "ops@example.com".to_string(),
+            "alice@example.com".to_string(),
+        ],
+        subject: "Subject".to_string(),
+        status,
+        error: (status != EmailSendStatus::Sent).then(|| "relay refused".to_string()),
+    }
+}
+
+pub async fn create_and_filter(repo: &dyn EmailLogRepo, org_id: Uuid) {
+    let sent = repo
+        .create(entry(
+            Some(org_id),
+            EmailKind::Invitation,
+            EmailSendStatus::Sent,
+        ))
+        .await
+        .expect("create entry");
+    repo.create(entry(
+        Some(org_id),
+        EmailKind::SpendAlert,
+        EmailSendStatus::Failed,
+    ))
+    .await
+    .unwrap();
+    repo.create(entry(
+        None,
+        EmailKind::DlqFailure,
+        EmailSendStatus::RateLimited,
+    ))
+    .await
+    .unwrap();
+
+    let all = repo
+        .list(EmailLogFilter::default(), ListParams::default())
+        .await
+        .expect("list all");
+    assert_eq!(all.items.len(), 3);
+
+    let for_org = repo
+        .list(
+            EmailLogFilter {
+                org_id: Some(org_id),
+                ..Default::default()
+            },
+            ListParams::default(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(for_org.items.len(), 2);
+
+    let invitations = repo
+        .list(
+            EmailLogFilter {
+                org_id: Some(org_id),
+                kind: Some(EmailKind::Invitation),
+                status: Some(EmailSendStatus::Sent),
+            },
+            ListParams::default(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(invitations.items.len(), 1);
+    let fetched = &invitations.items[0];
+    assert_eq!(fetched.id, sent.id);
+    assert_eq!(fetched.recipients, sent.recipients);
+    assert_eq!(fetched.created_at, sent.created_at);
+    assert!(fetched.error.is_none());
+
+    let rate_limited = repo
+        .list(
+            EmailLogFilter {
+                status: Some(EmailSendStatus::RateLimited),
+                ..Default::default()
+            },
+            ListParams::default(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(rate_limited.items.len(), 1);
+    assert_eq!(rate_limited.items[0].kind, EmailKind::DlqFailure);
+    assert!(rate_limited.items[0].org_id.is_none());
+}
+
+pub async fn list_paginates(repo: &dyn EmailLogRepo, org_id: Uuid) {
+    for _ in 0..3 {
+        repo.create(entry(
+            Some(org_id),
+            EmailKind::AccessReviewReminder,
+            EmailSendStatus::Sent,
+        ))
+        .await
+        .unwrap();
+    }
+
+    let page = repo
+        .list(
+            EmailLogFilter::default(),
+            ListParams {
+                limit: Some(2),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("list first page");
+    assert_eq!(page.items.len(), 2);
+    assert!(page.has_more);
+
+    let next = repo
+        .list(
+            EmailLogFilter::default(),
+            ListParams {
+                limit: Some(2),
+                cursor: page.cursors.next.clone(),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("list second page");
+    assert_eq!(next.items.len(), 1);
+    assert!(!next.has_more);
+}
+
+pub async fn delete_before_prunes(repo: &dyn EmailLogRepo, org_id: Uuid) {
+    for _ in 0..3 {
+        repo.create(entry(
+            Some(org_id),
+            EmailKind::Invitation,
+            EmailSendStatus::Sent,
+        ))
+        .await
+        .unwrap();
+    }
+
+    let deleted = repo
+        .delete_before(Utc::now() - Duration::days(1), 100, u64::MAX)
+        .await
+        .expect("delete nothing");
+    assert_eq!(deleted, 0);
+
+    let deleted = repo
+        .delete_before(Utc::now() + Duration::seconds(1), 2, u64::MAX)
+        .await
+        .expect("delete all in batches");
+    assert_eq!(deleted, 3);
+    assert!(
+        repo.list(EmailLogFilter::default(), ListParams::default())
+            .await
+            .unwrap()
+            .items
+            .is_empty()
+    );
+}
+
+#[cfg(all(test, feature = "database-sqlite"))]
+mod sqlite_tests {
+    use uuid::Uuid;
+
+    use crate::{
+        db::{
+            repos::OrganizationRepo,
+            sqlite::{SqliteEmailLogRepo, SqliteOrganizationRepo},
+            tests::harness::{create_sqlite_pool, run_sqlite_migrations},
+        },
+        models::CreateOrganization,
+    };
+
+    async fn create_repo() -> (SqliteEmailLogRepo, Uuid) {
+        let pool = create_sqlite_pool().await;
+        run_sqlite_migrations(&pool).await;
+        let org = SqliteOrganizationRepo::new(pool.clone())
+            .create(CreateOrganization {
+                slug: "acme".to_string(),
+                name: "Acme".to_string(),
+            })
+            .await
+            .expect("create org");
+        (SqliteEmailLogRepo::new(pool), org.id)
+    }
+
+    macro_rules! sqlite_test {
+        ($name:ident) => {
+            #[tokio::test]
+            async fn $name() {
+                let (repo, org_id) = create_repo().await;
+                super::$name(&repo, org_id).await;
+            }
+        };
+    }
+
+    sqlite_test!(create_and_filter);
+    sqlite_test!(list_paginates);
+    sqlite_test!(delete_before_prunes);
+}
+
+#[cfg(all(test, feature = "database-postgres"))]
+mod postgres_tests {
+    use uuid::Uuid;
+
+    use crate::{
+        db::{
+            postgres::{PostgresEmailLogRepo, PostgresOrganizationRepo},
+            repos::OrganizationRepo,
+            tests::harness::postgres::{create_isolated_postgres_pool, run_postgres_migrations},
+        },
+        models::CreateOrganization,
+    };
+
+    async fn create_repo() -> (PostgresEmailLogRepo, Uuid) {
+        let pool = create_isolated_postgres_pool().await;
+        run_postgres_migrations(&pool).await;
+        let org = PostgresOrganizationRepo::new(pool.clone(), None)
+            .create(CreateOrganization {
+                slug: "acme".to_string(),
+                name: "Acme".to_string(),
+            })
+            .await
+            .expect("create org");
+        (PostgresEmailLogRepo::new(pool, None), org.id)
+    }
+
+    macro_rules! postgres_test {
+        ($name:ident) => {
+            #[tokio::test]
+            #[ignore = "Requires Docker - run with `cargo test -- --ignored`"]
+            async fn $name() {
+                let (repo, org_id) = create_repo().await;
+                super::$name(&repo, org_id).await;
+            }
+        };
+    }
+
+    postgres_test!(create_and_filter);
+    postgres_test!(list_paginates);
+    postgres_test!(delete_before_prunes);
+}
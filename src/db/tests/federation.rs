@@ -0,0 +1,226 @@
+//! Shared tests for FederationRepo implementations
+
+use chrono::{NaiveDate, Utc};
+
+use crate::{
+    db::repos::{DateRange, FederationRepo},
+    models::{FederatedProviderHealth, FederatedUsage, FederationReport},
+    providers::{circuit_breaker::CircuitState, health_check::HealthStatus},
+};
+
+fn day(d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 3, d).unwrap()
+}
+
+fn usage(date: NaiveDate, model: &str, requests: i64) -> FederatedUsage {
+    FederatedUsage {
+        date,
+        model: model.to_string(),
+        total_cost_microcents: requests * 1_000,
+        input_tokens: requests * 10,
+        output_tokens: requests * 20,
+        total_tokens: requests * 30,
+        request_count: requests,
+    }
+}
+
+fn report(gateway_id: &str, usage: Vec<FederatedUsage>) -> FederationReport {
+    FederationReport {
+        gateway_id: gateway_id.to_string(),
+        region: Some("eu-west".to_string()),
+        version: "1.0.0".to_string(),
+        generated_at: Utc::now(),
+        usage,
+        providers: vec![FederatedProviderHealth {
+            provider: "openai".to_string(),
+            status: HealthStatus::Healthy,
+            latency_ms: Some(120),
+            circuit_state: Some(CircuitState::Closed),
+        }],
+    }
+}
+
+pub async fn record_report_upserts_gateway(repo: &dyn FederationRepo) {
+    let first_seen = Utc::now();
+    repo.record_report(&report("eu-west-1", vec![]), first_seen)
+        .await
+        .expect("first report");
+
+    let mut second = report("eu-west-1", vec![]);
+    second.version = "1.1.0".to_string();
+    second.providers.clear();
+    let later = first_seen + chrono::Duration::minutes(5);
+    repo.record_report(&second, later)
+        .await
+        .expect("second report");
+
+    let gateway = repo
+        .get_gateway("eu-west-1")
+        .await
+        .expect("get gateway")
+        .expect("gateway exists");
+    assert_eq!(gateway.version, "1.1.0");
+    assert!(gateway.providers.is_empty());
+    assert!(gateway.first_report_at < gateway.last_report_at);
+
+    assert!(repo.get_gateway("missing").await.unwrap().is_none());
+}
+
+pub async fn record_report_replaces_reported_days(repo: &dyn FederationRepo) {
+    repo.record_report(
+        &report(
+            "eu-west-1",
+            vec![
+                usage(day(1), "gpt-4o", 5),
+                usage(day(2), "gpt-4o", 3),
+                usage(day(2), "claude-sonnet", 1),
+            ],
+        ),
+        Utc::now(),
+    )
+    .await
+    .expect("first report");
+
+    // Day 2 is resent with updated totals and without claude-sonnet; day 1
+    // isn't included and must be left alone.
+    repo.record_report(
+        &report("eu-west-1", vec![usage(day(2), "gpt-4o", 7)]),
+        Utc::now(),
+    )
+    .await
+    .expect("second report");
+
+    let rows = repo
+        .list_usage(
+            DateRange {
+                start: day(1),
+                end: day(2),
+            },
+            None,
+        )
+        .await
+        .expect("list usage");
+    let summary: Vec<_> = rows
+        .iter()
+        .map(|r| (r.usage.date, r.usage.model.as_str(), r.usage.request_count))
+        .collect();
+    assert_eq!(summary, vec![(day(1), "gpt-4o", 5), (day(2), "gpt-4o", 7)]);
+}
+
+pub async fn list_usage_filters_by_gateway_and_range(repo: &dyn FederationRepo) {
+    repo.record_report(
+        &report(
+            "eu-west-1",
+            vec![usage(day(1), "gpt-4o", 1), usage(day(5), "gpt-4o", 2)],
+        ),
+        Utc::now(),
+    )
+    .await
+    .unwrap();
+    repo.record_report(
+        &report("us-east-1", vec![usage(day(1), "gpt-4o", 4)]),
+        Utc::now(),
+    )
+    .await
+    .unwrap();
+
+    let range = DateRange {
+        start: day(1),
+        end: day(3),
+    };
+    let all = repo.list_usage(range.clone(), None).await.unwrap();
+    assert_eq!(all.len(), 2);
+    assert_eq!(all[0].gateway_id, "eu-west-1");
+    assert_eq!(all[1].gateway_id, "us-east-1");
+
+    let us = repo.list_usage(range, Some("us-east-1")).await.unwrap();
+    assert_eq!(us.len(), 1);
+    assert_eq!(us[0].usage.request_count, 4);
+
+    let gateways = repo.list_gateways().await.unwrap();
+    let ids: Vec<_> = gateways.iter().map(|g| g.id.as_str()).collect();
+    assert_eq!(ids, vec!["eu-west-1", "us-east-1"]);
+}
+
+pub async fn delete_gateway_removes_usage(repo: &dyn FederationRepo) {
+    repo.record_report(
+        &report("eu-west-1", vec![usage(day(1), "gpt-4o", 1)]),
+        Utc::now(),
+    )
+    .await
+    .unwrap();
+
+    assert!(repo.delete_gateway("eu-west-1").await.unwrap());
+    assert!(!repo.delete_gateway("eu-west-1").await.unwrap());
+    assert!(repo.list_gateways().await.unwrap().is_empty());
+    let rows = repo
+        .list_usage(
+            DateRange {
+                start: day(1),
+                end: day(1),
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(rows.is_empty());
+}
+
+#[cfg(all(test, feature = "database-sqlite"))]
+mod sqlite_tests {
+    use crate::db::{
+        sqlite::SqliteFederationRepo,
+        tests::harness::{create_sqlite_pool, run_sqlite_migrations},
+    };
+
+    async fn create_repo() -> SqliteFederationRepo {
+        let pool = create_sqlite_pool().await;
+        run_sqlite_migrations(&pool).await;
+        SqliteFederationRepo::new(pool)
+    }
+
+    macro_rules! sqlite_test {
+        ($name:ident) => {
+            #[tokio::test]
+            async fn $name() {
+                let repo = create_repo().await;
+                super::$name(&repo).await;
+            }
+        };
+    }
+
+    sqlite_test!(record_report_upserts_gateway);
+    sqlite_test!(record_report_replaces_reported_days);
+    sqlite_test!(list_usage_filters_by_gateway_and_range);
+    sqlite_test!(delete_gateway_removes_usage);
+}
+
+#[cfg(all(test, feature = "database-postgres"))]
+mod postgres_tests {
+    use crate::db::{
+        postgres::PostgresFederationRepo,
+        tests::harness::postgres::{create_isolated_postgres_pool, run_postgres_migrations},
+    };
+
+    async fn create_repo() -> PostgresFederationRepo {
+        let pool = create_isolated_postgres_pool().await;
+        run_postgres_migrations(&pool).await;
+        PostgresFederationRepo::new(pool, None)
+    }
+
+    macro_rules! postgres_test {
+        ($name:ident) => {
+            #[tokio::test]
+            #[ignore = "Requires Docker - run with `cargo test -- --ignored`"]
+            async fn $name() {
+                let repo = create_repo().await;
+                super::$name(&repo).await;
+            }
+        };
+    }
+
+    postgres_test!(record_report_upserts_gateway);
+    postgres_test!(record_report_replaces_reported_days);
+    postgres_test!(list_usage_filters_by_gateway_and_range);
+    postgres_test!(delete_gateway_removes_usage);
+}
@@ -0,0 +1,218 @@
+//! Shared tests for FineTuningJobRepo implementations
+
+use uuid::Uuid;
+
+use crate::{
+    db::{error::DbError, repos::FineTuningJobRepo},
+    models::{CreateFineTuningJobRecord, FineTuningJobStatus},
+};
+
+fn create_input(org_id: Uuid, project_id: Option<Uuid>) -> CreateFineTuningJobRecord {
+    CreateFineTuningJobRecord {
+        provider: "openai".to_string(),
+        provider_job_id: format!("ftjob-{}", Uuid::new_v4().simple()),
+        org_id,
+        project_id,
+        team_id: None,
+        user_id: Some(Uuid::new_v4()),
+        api_key_id: None,
+        service_account_id: None,
+        model: "gpt-4o-mini-2024-07-18".to_string(),
+        training_file: "file-abc".to_string(),
+        validation_file: None,
+        status: "validating_files".to_string(),
+    }
+}
+
+fn succeeded() -> FineTuningJobStatus {
+    FineTuningJobStatus {
+        status: "succeeded".to_string(),
+        fine_tuned_model: Some("ft:gpt-4o-mini-2024-07-18:acme::abc123".to_string()),
+        trained_tokens: Some(120_000),
+    }
+}
+
+pub async fn create_and_get_round_trips(repo: &dyn FineTuningJobRepo) {
+    let org_id = Uuid::new_v4();
+    let project_id = Some(Uuid::new_v4());
+    let created = repo
+        .create(create_input(org_id, project_id))
+        .await
+        .expect("create job");
+    assert_eq!(created.org_id, org_id);
+    assert_eq!(created.project_id, project_id);
+    assert!(created.completed_at.is_none());
+
+    let fetched = repo
+        .get_by_id(created.id)
+        .await
+        .expect("get job")
+        .expect("job exists");
+    assert_eq!(fetched, created);
+
+    assert!(
+        repo.get_by_id(Uuid::new_v4())
+            .await
+            .expect("get missing job")
+            .is_none()
+    );
+}
+
+pub async fn list_scopes_to_org_and_project(repo: &dyn FineTuningJobRepo) {
+    let org_id = Uuid::new_v4();
+    let project_id = Uuid::new_v4();
+    let org_job = repo
+        .create(create_input(org_id, None))
+        .await
+        .expect("create org job");
+    let project_job = repo
+        .create(create_input(org_id, Some(project_id)))
+        .await
+        .expect("create project job");
+    repo.create(create_input(Uuid::new_v4(), None))
+        .await
+        .expect("create other org job");
+
+    let ids: Vec<_> = repo
+        .list(org_id, None, 10)
+        .await
+        .expect("list org jobs")
+        .into_iter()
+        .map(|j| j.id)
+        .collect();
+    assert_eq!(ids.len(), 2);
+    assert!(ids.contains(&org_job.id) && ids.contains(&project_job.id));
+
+    let project_jobs = repo
+        .list(org_id, Some(project_id), 10)
+        .await
+        .expect("list project jobs");
+    assert_eq!(project_jobs.len(), 1);
+    assert_eq!(project_jobs[0].id, project_job.id);
+
+    assert_eq!(
+        repo.list(org_id, None, 1)
+            .await
+            .expect("list limited")
+            .len(),
+        1
+    );
+}
+
+pub async fn update_status_stores_result(repo: &dyn FineTuningJobRepo) {
+    let created = repo
+        .create(create_input(Uuid::new_v4(), None))
+        .await
+        .expect("create job");
+
+    let updated = repo
+        .update_status(created.id, &succeeded())
+        .await
+        .expect("update status");
+    assert_eq!(updated.status, "succeeded");
+    assert_eq!(updated.trained_tokens, Some(120_000));
+    assert_eq!(
+        updated.fine_tuned_model.as_deref(),
+        Some("ft:gpt-4o-mini-2024-07-18:acme::abc123")
+    );
+    assert_eq!(updated.created_at, created.created_at);
+
+    let err = repo
+        .update_status(Uuid::new_v4(), &succeeded())
+        .await
+        .expect_err("missing job should fail");
+    assert!(matches!(err, DbError::NotFound));
+}
+
+pub async fn mark_completed_only_once_after_finishing(repo: &dyn FineTuningJobRepo) {
+    let created = repo
+        .create(create_input(Uuid::new_v4(), None))
+        .await
+        .expect("create job");
+
+    // Running jobs can't be completed
+    assert!(!repo.mark_completed(created.id).await.expect("mark running"));
+    assert_eq!(
+        repo.list_incomplete(10)
+            .await
+            .expect("list incomplete")
+            .len(),
+        1
+    );
+
+    repo.update_status(created.id, &succeeded())
+        .await
+        .expect("update status");
+    assert!(
+        repo.mark_completed(created.id)
+            .await
+            .expect("mark finished")
+    );
+    assert!(!repo.mark_completed(created.id).await.expect("mark again"));
+
+    assert!(
+        repo.list_incomplete(10)
+            .await
+            .expect("list incomplete")
+            .is_empty()
+    );
+}
+
+#[cfg(all(test, feature = "database-sqlite"))]
+mod sqlite_tests {
+    use crate::db::{
+        sqlite::SqliteFineTuningJobRepo,
+        tests::harness::{create_sqlite_pool, run_sqlite_migrations},
+    };
+
+    async fn create_repo() -> SqliteFineTuningJobRepo {
+        let pool = create_sqlite_pool().await;
+        run_sqlite_migrations(&pool).await;
+        SqliteFineTuningJobRepo::new(pool)
+    }
+
+    macro_rules! sqlite_test {
+        ($name:ident) => {
+            #[tokio::test]
+            async fn $name() {
+                let repo = create_repo().await;
+                super::$name(&repo).await;
+            }
+        };
+    }
+
+    sqlite_test!(create_and_get_round_trips);
+    sqlite_test!(list_scopes_to_org_and_project);
+    sqlite_test!(update_status_stores_result);
+    sqlite_test!(mark_completed_only_once_after_finishing);
+}
+
+#[cfg(all(test, feature = "database-postgres"))]
+mod postgres_tests {
+    use crate::db::{
+        postgres::PostgresFineTuningJobRepo,
+        tests::harness::postgres::{create_isolated_postgres_pool, run_postgres_migrations},
+    };
+
+    async fn create_repo() -> PostgresFineTuningJobRepo {
+        let pool = create_isolated_postgres_pool().await;
+        run_postgres_migrations(&pool).await;
+        PostgresFineTuningJobRepo::new(pool, None)
+    }
+
+    macro_rules! postgres_test {
+        ($name:ident) => {
+            #[tokio::test]
+            #[ignore = "Requires Docker - run with `cargo test -- --ignored`"]
+            async fn $name() {
+                let repo = create_repo().await;
+                super::$name(&repo).await;
+            }
+        };
+    }
+
+    postgres_test!(create_and_get_round_trips);
+    postgres_test!(list_scopes_to_org_and_project);
+    postgres_test!(update_status_stores_result);
+    postgres_test!(mark_completed_only_once_after_finishing);
+}
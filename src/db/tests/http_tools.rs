@@ -0,0 +1,191 @@
+//! Shared tests for HttpToolRepo implementations
+
+use std::collections::HashMap;
+
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    db::{error::DbError, repos::HttpToolRepo},
+    models::{CreateHttpTool, UpdateHttpTool},
+};
+
+fn create_input(name: &str) -> CreateHttpTool {
+    CreateHttpTool {
+        name: name.to_string(),
+        description: Some("Look up an order by its id".to_string()),
+        url: "https://orders.example.com/lookup".to_string(),
+        headers: HashMap::from([("Authorization".to_string(), "Bearer secret".to_string())]),
+        parameters: Some(json!({
+            "type": "object",
+            "properties": { "order_id": { "type": "string" } }
+        })),
+        timeout_secs: 10,
+        max_response_bytes: 4096,
+    }
+}
+
+pub async fn http_tool_crud_round_trips(repo: &dyn HttpToolRepo) {
+    let org_id = Uuid::new_v4();
+    let created = repo
+        .create(org_id, create_input("lookup_order"))
+        .await
+        .expect("create tool");
+    assert_eq!(created.org_id, org_id);
+
+    let fetched = repo
+        .get_by_id(created.id)
+        .await
+        .expect("get tool")
+        .expect("tool exists");
+    assert_eq!(fetched.name, "lookup_order");
+    assert_eq!(fetched.headers["Authorization"], "Bearer secret");
+    assert_eq!(
+        fetched.parameters.as_ref().unwrap()["properties"]["order_id"]["type"],
+        "string"
+    );
+    assert_eq!(fetched.timeout_secs, 10);
+    assert_eq!(fetched.max_response_bytes, 4096);
+
+    repo.create(org_id, create_input("cancel_order"))
+        .await
+        .expect("create second tool");
+    repo.create(Uuid::new_v4(), create_input("lookup_order"))
+        .await
+        .expect("same name in another org");
+    let names: Vec<_> = repo
+        .list_by_org(org_id)
+        .await
+        .expect("list tools")
+        .into_iter()
+        .map(|t| t.name)
+        .collect();
+    assert_eq!(names, vec!["cancel_order", "lookup_order"]);
+
+    let updated = repo
+        .update(
+            created.id,
+            UpdateHttpTool {
+                url: Some("https://orders.example.com/v2/lookup".to_string()),
+                headers: Some(HashMap::new()),
+                timeout_secs: Some(5),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("update tool");
+    assert_eq!(updated.url, "https://orders.example.com/v2/lookup");
+    assert!(updated.headers.is_empty());
+    assert_eq!(updated.timeout_secs, 5);
+    assert_eq!(updated.name, "lookup_order");
+    assert_eq!(updated.max_response_bytes, 4096);
+    assert!(updated.parameters.is_some());
+
+    repo.delete(created.id).await.expect("delete tool");
+    assert!(
+        repo.get_by_id(created.id)
+            .await
+            .expect("get deleted tool")
+            .is_none()
+    );
+}
+
+pub async fn http_tool_names_are_unique_per_org(repo: &dyn HttpToolRepo) {
+    let org_id = Uuid::new_v4();
+    repo.create(org_id, create_input("lookup_order"))
+        .await
+        .expect("create tool");
+    let other = repo
+        .create(org_id, create_input("cancel_order"))
+        .await
+        .expect("create second tool");
+
+    let err = repo
+        .create(org_id, create_input("lookup_order"))
+        .await
+        .expect_err("duplicate name should fail");
+    assert!(matches!(err, DbError::Conflict(_)));
+
+    let err = repo
+        .update(
+            other.id,
+            UpdateHttpTool {
+                name: Some("lookup_order".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect_err("rename to an existing name should fail");
+    assert!(matches!(err, DbError::Conflict(_)));
+}
+
+pub async fn missing_http_tool_is_not_found(repo: &dyn HttpToolRepo) {
+    let err = repo
+        .update(Uuid::new_v4(), UpdateHttpTool::default())
+        .await
+        .expect_err("update missing tool");
+    assert!(matches!(err, DbError::NotFound));
+
+    let err = repo
+        .delete(Uuid::new_v4())
+        .await
+        .expect_err("delete missing tool");
+    assert!(matches!(err, DbError::NotFound));
+}
+
+#[cfg(all(test, feature = "database-sqlite"))]
+mod sqlite_tests {
+    use crate::db::{
+        sqlite::SqliteHttpToolRepo,
+        tests::harness::{create_sqlite_pool, run_sqlite_migrations},
+    };
+
+    async fn create_repo() -> SqliteHttpToolRepo {
+        let pool = create_sqlite_pool().await;
+        run_sqlite_migrations(&pool).await;
+        SqliteHttpToolRepo::new(pool)
+    }
+
+    macro_rules! sqlite_test {
+        ($name:ident) => {
+            #[tokio::test]
+            async fn $name() {
+                let repo = create_repo().await;
+                super::$name(&repo).await;
+            }
+        };
+    }
+
+    sqlite_test!(http_tool_crud_round_trips);
+    sqlite_test!(http_tool_names_are_unique_per_org);
+    sqlite_test!(missing_http_tool_is_not_found);
+}
+
+#[cfg(all(test, feature = "database-postgres"))]
+mod postgres_tests {
+    use crate::db::{
+        postgres::PostgresHttpToolRepo,
+        tests::harness::postgres::{create_isolated_postgres_pool, run_postgres_migrations},
+    };
+
+    async fn create_repo() -> PostgresHttpToolRepo {
+        let pool = create_isolated_postgres_pool().await;
+        run_postgres_migrations(&pool).await;
+        PostgresHttpToolRepo::new(pool, None)
+    }
+
+    macro_rules! postgres_test {
+        ($name:ident) => {
+            #[tokio::test]
+            #[ignore = "Requires Docker - run with `cargo test -- --ignored`"]
+            async fn $name() {
+                let repo = create_repo().await;
+                super::$name(&repo).await;
+            }
+        };
+    }
+
+    postgres_test!(http_tool_crud_round_trips);
+    postgres_test!(http_tool_names_are_unique_per_org);
+    postgres_test!(missing_http_tool_is_not_found);
+}
@@ -0,0 +1,193 @@
+//! Shared tests for IdempotencyKeyRepo implementations
+
+use chrono::{Duration, Utc};
+
+use crate::{
+    db::repos::IdempotencyKeyRepo,
+    models::{IdempotencyRecord, IdempotencyStatus, IdempotentResponse},
+};
+
+fn record(key: &str, request_hash: &str, ttl: Duration) -> IdempotencyRecord {
+    let now = Utc::now();
+    IdempotencyRecord {
+        key: key.to_string(),
+        request_hash: request_hash.to_string(),
+        status: IdempotencyStatus::InProgress,
+        response: None,
+        created_at: now,
+        expires_at: now + ttl,
+    }
+}
+
+pub async fn claim_then_complete(repo: &dyn IdempotencyKeyRepo) {
+    let first = record("key-a", "hash-1", Duration::hours(1));
+    assert!(repo.claim(&first).await.expect("claim").is_none());
+
+    // A repeat sees the in-progress claim
+    let held = repo
+        .claim(&record("key-a", "hash-1", Duration::hours(1)))
+        .await
+        .expect("claim repeat")
+        .expect("key is held");
+    assert_eq!(held.status, IdempotencyStatus::InProgress);
+    assert_eq!(held.request_hash, "hash-1");
+    assert!(held.response.is_none());
+
+    let response = IdempotentResponse {
+        status: 200,
+        content_type: Some("application/json".to_string()),
+        body: br#"{"id":"chatcmpl-1"}"#.to_vec(),
+    };
+    repo.complete("key-a", Some(&response))
+        .await
+        .expect("complete");
+
+    let done = repo
+        .claim(&record("key-a", "hash-1", Duration::hours(1)))
+        .await
+        .expect("claim after completion")
+        .expect("key is held");
+    assert_eq!(done.status, IdempotencyStatus::Completed);
+    assert_eq!(done.response, Some(response));
+}
+
+pub async fn complete_without_response(repo: &dyn IdempotencyKeyRepo) {
+    repo.claim(&record("key-a", "hash-1", Duration::hours(1)))
+        .await
+        .expect("claim");
+    repo.complete("key-a", None).await.expect("complete");
+
+    let done = repo
+        .claim(&record("key-a", "hash-1", Duration::hours(1)))
+        .await
+        .expect("claim after completion")
+        .expect("key is held");
+    assert_eq!(done.status, IdempotencyStatus::Completed);
+    assert!(done.response.is_none());
+}
+
+pub async fn release_frees_in_progress_claims_only(repo: &dyn IdempotencyKeyRepo) {
+    repo.claim(&record("key-a", "hash-1", Duration::hours(1)))
+        .await
+        .expect("claim");
+    repo.release("key-a").await.expect("release");
+    assert!(
+        repo.claim(&record("key-a", "hash-2", Duration::hours(1)))
+            .await
+            .expect("claim after release")
+            .is_none()
+    );
+
+    // Completed keys stay put
+    repo.complete("key-a", None).await.expect("complete");
+    repo.release("key-a").await.expect("release");
+    assert!(
+        repo.claim(&record("key-a", "hash-2", Duration::hours(1)))
+            .await
+            .expect("claim after completion")
+            .is_some()
+    );
+}
+
+pub async fn expired_claims_are_replaced(repo: &dyn IdempotencyKeyRepo) {
+    repo.claim(&record("key-a", "hash-1", Duration::seconds(-1)))
+        .await
+        .expect("claim");
+    repo.complete("key-a", None).await.expect("complete");
+
+    assert!(
+        repo.claim(&record("key-a", "hash-2", Duration::hours(1)))
+            .await
+            .expect("claim expired key")
+            .is_none()
+    );
+    let held = repo
+        .claim(&record("key-a", "hash-2", Duration::hours(1)))
+        .await
+        .expect("claim repeat")
+        .expect("key is held");
+    assert_eq!(held.request_hash, "hash-2");
+    assert_eq!(held.status, IdempotencyStatus::InProgress);
+}
+
+pub async fn delete_expired_removes_only_expired(repo: &dyn IdempotencyKeyRepo) {
+    repo.claim(&record("old", "hash-1", Duration::seconds(-1)))
+        .await
+        .expect("claim old");
+    repo.claim(&record("new", "hash-2", Duration::hours(1)))
+        .await
+        .expect("claim new");
+
+    let deleted = repo
+        .delete_expired(Utc::now())
+        .await
+        .expect("delete expired");
+    assert_eq!(deleted, 1);
+    assert!(
+        repo.claim(&record("new", "hash-2", Duration::hours(1)))
+            .await
+            .expect("claim new again")
+            .is_some()
+    );
+}
+
+#[cfg(all(test, feature = "database-sqlite"))]
+mod sqlite_tests {
+    use crate::db::{
+        sqlite::SqliteIdempotencyKeyRepo,
+        tests::harness::{create_sqlite_pool, run_sqlite_migrations},
+    };
+
+    async fn create_repo() -> SqliteIdempotencyKeyRepo {
+        let pool = create_sqlite_pool().await;
+        run_sqlite_migrations(&pool).await;
+        SqliteIdempotencyKeyRepo::new(pool)
+    }
+
+    macro_rules! sqlite_test {
+        ($name:ident) => {
+            #[tokio::test]
+            async fn $name() {
+                let repo = create_repo().await;
+                super::$name(&repo).await;
+            }
+        };
+    }
+
+    sqlite_test!(claim_then_complete);
+    sqlite_test!(complete_without_response);
+    sqlite_test!(release_frees_in_progress_claims_only);
+    sqlite_test!(expired_claims_are_replaced);
+    sqlite_test!(delete_expired_removes_only_expired);
+}
+
+#[cfg(all(test, feature = "database-postgres"))]
+mod postgres_tests {
+    use crate::db::{
+        postgres::PostgresIdempotencyKeyRepo,
+        tests::harness::postgres::{create_isolated_postgres_pool, run_postgres_migrations},
+    };
+
+    async fn create_repo() -> PostgresIdempotencyKeyRepo {
+        let pool = create_isolated_postgres_pool().await;
+        run_postgres_migrations(&pool).await;
+        PostgresIdempotencyKeyRepo::new(pool)
+    }
+
+    macro_rules! postgres_test {
+        ($name:ident) => {
+            #[tokio::test]
+            #[ignore = "Requires Docker - run with `cargo test -- --ignored`"]
+            async fn $name() {
+                let repo = create_repo().await;
+                super::$name(&repo).await;
+            }
+        };
+    }
+
+    postgres_test!(claim_then_complete);
+    postgres_test!(complete_without_response);
+    postgres_test!(release_frees_in_progress_claims_only);
+    postgres_test!(expired_claims_are_replaced);
+    postgres_test!(delete_expired_removes_only_expired);
+}
@@ -0,0 +1,292 @@
+//! Shared tests for InvitationRepo implementations
+
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        error::DbError,
+        repos::{InvitationRepo, ListParams},
+    },
+    models::{InvitationStatus, NewInvitation},
+};
+
+fn invitation(org_id: Uuid, email: &str, token_hash: &str, ttl: Duration) -> NewInvitation {
+    NewInvitation {
+        org_id,
+        team_id: None,
+        email: email.to_string(),
+        role: "member".to_string(),
+        invited_by: None,
+        token_hash: token_hash.to_string(),
+        expires_at: Utc::now() + ttl,
+    }
+}
+
+pub async fn create_and_lookup(repo: &dyn InvitationRepo, org_id: Uuid) {
+    let created = repo
+        .create(invitation(
+            org_id,
+            "alice@example.com",
+            "hash-a",
+            Duration::days(7),
+        ))
+        .await
+        .expect("create invitation");
+    assert_eq!(created.status, InvitationStatus::Pending);
+    assert_eq!(created.send_count, 0);
+
+    let fetched = repo
+        .get_by_id(created.id)
+        .await
+        .expect("get invitation")
+        .expect("invitation exists");
+    assert_eq!(fetched.org_id, org_id);
+    assert_eq!(fetched.email, "alice@example.com");
+    assert_eq!(fetched.expires_at, created.expires_at);
+    assert_eq!(fetched.created_at, created.created_at);
+
+    let by_token = repo
+        .get_by_token_hash("hash-a")
+        .await
+        .expect("get by token")
+        .expect("invitation exists");
+    assert_eq!(by_token.id, created.id);
+    assert!(repo.get_by_token_hash("hash-b").await.unwrap().is_none());
+
+    // Token hashes are unique
+    let err = repo
+        .create(invitation(
+            org_id,
+            "bob@example.com",
+            "hash-a",
+            Duration::days(7),
+        ))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DbError::Conflict(_)), "got {err:?}");
+
+    repo.mark_sent(created.id).await.expect("mark sent");
+    let sent = repo.get_by_id(created.id).await.unwrap().unwrap();
+    assert_eq!(sent.send_count, 1);
+    assert!(sent.last_sent_at.is_some());
+}
+
+pub async fn expiry_and_pending_lookup(repo: &dyn InvitationRepo, org_id: Uuid) {
+    let expired = repo
+        .create(invitation(
+            org_id,
+            "carol@example.com",
+            "hash-expired",
+            Duration::hours(-1),
+        ))
+        .await
+        .unwrap();
+    let pending = repo
+        .create(invitation(
+            org_id,
+            "carol@example.com",
+            "hash-pending",
+            Duration::hours(1),
+        ))
+        .await
+        .unwrap();
+    let revoked = repo
+        .create(invitation(
+            org_id,
+            "carol@example.com",
+            "hash-revoked",
+            Duration::hours(1),
+        ))
+        .await
+        .unwrap();
+    repo.revoke(revoked.id).await.expect("revoke");
+
+    let fetched = repo.get_by_id(expired.id).await.unwrap().unwrap();
+    assert_eq!(fetched.status, InvitationStatus::Expired);
+
+    let found = repo
+        .list_pending_by_email("carol@example.com", Utc::now())
+        .await
+        .expect("list pending");
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].id, pending.id);
+
+    // Resending renews an expired invitation under a new token
+    let renewed = repo
+        .refresh_token(expired.id, "hash-renewed", Utc::now() + Duration::days(1))
+        .await
+        .expect("refresh token");
+    assert_eq!(renewed.status, InvitationStatus::Pending);
+    assert!(
+        repo.get_by_token_hash("hash-expired")
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert_eq!(
+        repo.list_pending_by_email("carol@example.com", Utc::now())
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+}
+
+pub async fn accept_and_revoke_only_pending(repo: &dyn InvitationRepo, org_id: Uuid) {
+    let created = repo
+        .create(invitation(
+            org_id,
+            "dave@example.com",
+            "hash-d",
+            Duration::days(1),
+        ))
+        .await
+        .unwrap();
+    let user_id = Uuid::new_v4();
+
+    assert!(repo.mark_accepted(created.id, user_id).await.unwrap());
+    // A second sign-in racing the first doesn't accept it again
+    assert!(!repo.mark_accepted(created.id, user_id).await.unwrap());
+
+    let accepted = repo.get_by_id(created.id).await.unwrap().unwrap();
+    assert_eq!(accepted.status, InvitationStatus::Accepted);
+    assert_eq!(accepted.accepted_by, Some(user_id));
+    assert!(accepted.accepted_at.is_some());
+
+    assert!(matches!(
+        repo.revoke(created.id).await,
+        Err(DbError::NotFound)
+    ));
+    assert!(matches!(
+        repo.refresh_token(created.id, "hash-d2", Utc::now() + Duration::days(1))
+            .await,
+        Err(DbError::NotFound)
+    ));
+}
+
+pub async fn list_by_org_paginates(repo: &dyn InvitationRepo, org_id: Uuid) {
+    for i in 0..3 {
+        repo.create(invitation(
+            org_id,
+            &format!("user{i}@example.com"),
+            &format!("hash-{i}"),
+            Duration::days(1),
+        ))
+        .await
+        .unwrap();
+    }
+
+    let page = repo
+        .list_by_org(
+            org_id,
+            ListParams {
+                limit: Some(2),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("list first page");
+    assert_eq!(page.items.len(), 2);
+    assert!(page.has_more);
+
+    let next = repo
+        .list_by_org(
+            org_id,
+            ListParams {
+                limit: Some(2),
+                cursor: page.cursors.next.clone(),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("list second page");
+    assert_eq!(next.items.len(), 1);
+    assert!(!next.has_more);
+    assert!(next.items.iter().all(|i| i.org_id == org_id));
+}
+
+#[cfg(all(test, feature = "database-sqlite"))]
+mod sqlite_tests {
+    use uuid::Uuid;
+
+    use crate::{
+        db::{
+            repos::OrganizationRepo,
+            sqlite::{SqliteInvitationRepo, SqliteOrganizationRepo},
+            tests::harness::{create_sqlite_pool, run_sqlite_migrations},
+        },
+        models::CreateOrganization,
+    };
+
+    async fn create_repo() -> (SqliteInvitationRepo, Uuid) {
+        let pool = create_sqlite_pool().await;
+        run_sqlite_migrations(&pool).await;
+        let org = SqliteOrganizationRepo::new(pool.clone())
+            .create(CreateOrganization {
+                slug: "acme".to_string(),
+                name: "Acme".to_string(),
+            })
+            .await
+            .expect("create org");
+        (SqliteInvitationRepo::new(pool), org.id)
+    }
+
+    macro_rules! sqlite_test {
+        ($name:ident) => {
+            #[tokio::test]
+            async fn $name() {
+                let (repo, org_id) = create_repo().await;
+                super::$name(&repo, org_id).await;
+            }
+        };
+    }
+
+    sqlite_test!(create_and_lookup);
+    sqlite_test!(expiry_and_pending_lookup);
+    sqlite_test!(accept_and_revoke_only_pending);
+    sqlite_test!(list_by_org_paginates);
+}
+
+#[cfg(all(test, feature = "database-postgres"))]
+mod postgres_tests {
+    use uuid::Uuid;
+
+    use crate::{
+        db::{
+            postgres::{PostgresInvitationRepo, PostgresOrganizationRepo},
+            repos::OrganizationRepo,
+            tests::harness::postgres::{create_isolated_postgres_pool, run_postgres_migrations},
+        },
+        models::CreateOrganization,
+    };
+
+    async fn create_repo() -> (PostgresInvitationRepo, Uuid) {
+        let pool = create_isolated_postgres_pool().await;
+        run_postgres_migrations(&pool).await;
+        let org = PostgresOrganizationRepo::new(pool.clone(), None)
+            .create(CreateOrganization {
+                slug: "acme".to_string(),
+                name: "Acme".to_string(),
+            })
+            .await
+            .expect("create org");
+        (PostgresInvitationRepo::new(pool, None), org.id)
+    }
+
+    macro_rules! postgres_test {
+        ($name:ident) => {
+            #[tokio::test]
+            #[ignore = "Requires Docker - run with `cargo test -- --ignored`"]
+            async fn $name() {
+                let (repo, org_id) = create_repo().await;
+                super::$name(&repo, org_id).await;
+            }
+        };
+    }
+
+    postgres_test!(create_and_lookup);
+    postgres_test!(expiry_and_pending_lookup);
+    postgres_test!(accept_and_revoke_only_pending);
+    postgres_test!(list_by_org_paginates);
+}
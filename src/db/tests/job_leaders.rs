@@ -0,0 +1,108 @@
+//! Shared tests for JobLeaderRepo implementations
+
+use chrono::{Duration, DurationRound, Utc};
+
+use crate::db::repos::JobLeaderRepo;
+
+pub async fn record_run_tracks_handover(repo: &dyn JobLeaderRepo) {
+    let start = Utc::now()
+        .duration_trunc(Duration::milliseconds(1))
+        .unwrap();
+
+    repo.record_run("retention", "node-a", start)
+        .await
+        .expect("record first run");
+    repo.record_run("retention", "node-a", start + Duration::minutes(5))
+        .await
+        .expect("record second run");
+
+    let leaders = repo.list().await.expect("list leaders");
+    assert_eq!(leaders.len(), 1);
+    assert_eq!(leaders[0].job, "retention");
+    assert_eq!(leaders[0].node_id, "node-a");
+    // The same node running again keeps its takeover time
+    assert_eq!(leaders[0].acquired_at, start);
+    assert_eq!(leaders[0].last_run_at, start + Duration::minutes(5));
+
+    // Another node taking over resets it
+    repo.record_run("retention", "node-b", start + Duration::minutes(10))
+        .await
+        .expect("record handover");
+    let leaders = repo.list().await.expect("list leaders");
+    assert_eq!(leaders[0].node_id, "node-b");
+    assert_eq!(leaders[0].acquired_at, start + Duration::minutes(10));
+    assert_eq!(leaders[0].last_run_at, start + Duration::minutes(10));
+}
+
+pub async fn list_orders_by_job(repo: &dyn JobLeaderRepo) {
+    let now = Utc::now();
+    for job in ["vector_store_cleanup", "dlq_retry", "retention"] {
+        repo.record_run(job, "node-a", now)
+            .await
+            .expect("record run");
+    }
+
+    let jobs: Vec<_> = repo
+        .list()
+        .await
+        .expect("list leaders")
+        .into_iter()
+        .map(|l| l.job)
+        .collect();
+    assert_eq!(jobs, ["dlq_retry", "retention", "vector_store_cleanup"]);
+}
+
+#[cfg(all(test, feature = "database-sqlite"))]
+mod sqlite_tests {
+    use crate::db::{
+        sqlite::SqliteJobLeaderRepo,
+        tests::harness::{create_sqlite_pool, run_sqlite_migrations},
+    };
+
+    async fn create_repo() -> SqliteJobLeaderRepo {
+        let pool = create_sqlite_pool().await;
+        run_sqlite_migrations(&pool).await;
+        SqliteJobLeaderRepo::new(pool)
+    }
+
+    macro_rules! sqlite_test {
+        ($name:ident) => {
+            #[tokio::test]
+            async fn $name() {
+                let repo = create_repo().await;
+                super::$name(&repo).await;
+            }
+        };
+    }
+
+    sqlite_test!(record_run_tracks_handover);
+    sqlite_test!(list_orders_by_job);
+}
+
+#[cfg(all(test, feature = "database-postgres"))]
+mod postgres_tests {
+    use crate::db::{
+        postgres::PostgresJobLeaderRepo,
+        tests::harness::postgres::{create_isolated_postgres_pool, run_postgres_migrations},
+    };
+
+    async fn create_repo() -> PostgresJobLeaderRepo {
+        let pool = create_isolated_postgres_pool().await;
+        run_postgres_migrations(&pool).await;
+        PostgresJobLeaderRepo::new(pool, None)
+    }
+
+    macro_rules! postgres_test {
+        ($name:ident) => {
+            #[tokio::test]
+            #[ignore = "Requires Docker - run with `cargo test -- --ignored`"]
+            async fn $name() {
+                let repo = create_repo().await;
+                super::$name(&repo).await;
+            }
+        };
+    }
+
+    postgres_test!(record_run_tracks_handover);
+    postgres_test!(list_orders_by_job);
+}
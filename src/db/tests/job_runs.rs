@@ -0,0 +1,171 @@
+//! Shared tests for JobRunRepo implementations
+
+use chrono::{DateTime, Duration, DurationRound, Utc};
+
+use crate::{
+    db::repos::JobRunRepo,
+    models::{CreateJobRun, JobRunStatus, JobTrigger},
+};
+
+fn run_input(job: &str, started_at: DateTime<Utc>, status: JobRunStatus) -> CreateJobRun {
+    CreateJobRun {
+        job: job.to_string(),
+        node_id: "node-a".to_string(),
+        triggered_by: JobTrigger::Scheduled,
+        status,
+        summary: None,
+        error: None,
+        started_at,
+        finished_at: started_at + Duration::milliseconds(1500),
+    }
+}
+
+pub async fn create_list_and_latest(repo: &dyn JobRunRepo) {
+    let start = Utc::now()
+        .duration_trunc(Duration::milliseconds(1))
+        .unwrap();
+
+    let created = repo
+        .create(CreateJobRun {
+            triggered_by: JobTrigger::Manual,
+            summary: Some("Deleted 3 usage records".to_string()),
+            ..run_input("retention", start, JobRunStatus::Succeeded)
+        })
+        .await
+        .expect("create run");
+    assert_eq!(created.duration_ms, 1500);
+    assert_eq!(created.triggered_by, JobTrigger::Manual);
+
+    repo.create(CreateJobRun {
+        error: Some("database is locked".to_string()),
+        ..run_input(
+            "retention",
+            start + Duration::minutes(5),
+            JobRunStatus::Failed,
+        )
+    })
+    .await
+    .expect("create failed run");
+    repo.create(run_input("dlq_retry", start, JobRunStatus::Succeeded))
+        .await
+        .expect("create other job run");
+
+    let runs = repo.list("retention", 10).await.expect("list runs");
+    assert_eq!(runs.len(), 2);
+    assert_eq!(runs[0].status, JobRunStatus::Failed);
+    assert_eq!(runs[0].error.as_deref(), Some("database is locked"));
+    assert_eq!(runs[1].id, created.id);
+    assert_eq!(runs[1].summary.as_deref(), Some("Deleted 3 usage records"));
+    assert_eq!(runs[1].started_at, start);
+
+    assert_eq!(repo.list("retention", 1).await.unwrap().len(), 1);
+    assert!(repo.list("unknown", 10).await.unwrap().is_empty());
+
+    let latest = repo.latest().await.expect("latest runs");
+    assert_eq!(latest.len(), 2);
+    assert_eq!(latest[0].job, "dlq_retry");
+    assert_eq!(latest[1].job, "retention");
+    assert_eq!(latest[1].status, JobRunStatus::Failed);
+}
+
+pub async fn prune_keeps_most_recent(repo: &dyn JobRunRepo) {
+    let start = Utc::now()
+        .duration_trunc(Duration::milliseconds(1))
+        .unwrap();
+    for i in 0..5 {
+        repo.create(run_input(
+            "dlq_retry",
+            start + Duration::minutes(i),
+            JobRunStatus::Succeeded,
+        ))
+        .await
+        .expect("create run");
+    }
+    repo.create(run_input("retention", start, JobRunStatus::Succeeded))
+        .await
+        .expect("create other job run");
+
+    assert_eq!(repo.prune("dlq_retry", 2).await.unwrap(), 3);
+    let runs = repo.list("dlq_retry", 10).await.unwrap();
+    assert_eq!(runs.len(), 2);
+    assert_eq!(runs[0].started_at, start + Duration::minutes(4));
+    assert_eq!(runs[1].started_at, start + Duration::minutes(3));
+    assert_eq!(repo.list("retention", 10).await.unwrap().len(), 1);
+}
+
+pub async fn pause_and_resume(repo: &dyn JobRunRepo) {
+    let now = Utc::now();
+    assert!(repo.list_paused().await.unwrap().is_empty());
+
+    repo.pause("retention", now).await.expect("pause");
+    repo.pause("retention", now).await.expect("pause twice");
+    repo.pause("dlq_retry", now).await.expect("pause other job");
+    assert_eq!(
+        repo.list_paused().await.unwrap(),
+        vec!["dlq_retry".to_string(), "retention".to_string()]
+    );
+
+    assert!(repo.resume("retention").await.unwrap());
+    assert!(!repo.resume("retention").await.unwrap());
+    assert_eq!(
+        repo.list_paused().await.unwrap(),
+        vec!["dlq_retry".to_string()]
+    );
+}
+
+#[cfg(all(test, feature = "database-sqlite"))]
+mod sqlite_tests {
+    use crate::db::{
+        sqlite::SqliteJobRunRepo,
+        tests::harness::{create_sqlite_pool, run_sqlite_migrations},
+    };
+
+    async fn create_repo() -> SqliteJobRunRepo {
+        let pool = create_sqlite_pool().await;
+        run_sqlite_migrations(&pool).await;
+        SqliteJobRunRepo::new(pool)
+    }
+
+    macro_rules! sqlite_test {
+        ($name:ident) => {
+            #[tokio::test]
+            async fn $name() {
+                let repo = create_repo().await;
+                super::$name(&repo).await;
+            }
+        };
+    }
+
+    sqlite_test!(create_list_and_latest);
+    sqlite_test!(prune_keeps_most_recent);
+    sqlite_test!(pause_and_resume);
+}
+
+#[cfg(all(test, feature = "database-postgres"))]
+mod postgres_tests {
+    use crate::db::{
+        postgres::PostgresJobRunRepo,
+        tests::harness::postgres::{create_isolated_postgres_pool, run_postgres_migrations},
+    };
+
+    async fn create_repo() -> PostgresJobRunRepo {
+        let pool = create_isolated_postgres_pool().await;
+        run_postgres_migrations(&pool).await;
+        PostgresJobRunRepo::new(pool, None)
+    }
+
+    macro_rules! postgres_test {
+        ($name:ident) => {
+            #[tokio::test]
+            #[ignore = "Requires Docker - run with `cargo test -- --ignored`"]
+            async fn $name() {
+                let repo = create_repo().await;
+                super::$name(&repo).await;
+            }
+        };
+    }
+
+    postgres_test!(create_list_and_latest);
+    postgres_test!(prune_keeps_most_recent);
+    postgres_test!(pause_and_resume);
+}
@@ -21,19 +21,44 @@
 //! cargo test -- --include-ignored  # Run all tests
 //! ```
 
+mod admin_passkeys;
+mod admin_totp;
+mod agents;
 mod api_keys;
 mod audit_logs;
+mod client_cert_mappings;
 mod containers;
 mod conversations;
+mod email_log;
+mod federation;
+mod fine_tuning_jobs;
 pub mod harness;
+mod http_tools;
+mod idempotency_keys;
+mod invitations;
+mod job_leaders;
+mod job_runs;
+mod model_catalog_entries;
 mod model_pricing;
 mod org_rbac_policies;
+mod org_request_policies;
 mod organizations;
 mod projects;
+mod provider_files;
+mod provider_probes;
+mod provider_settings;
+mod provider_usage_imports;
 mod providers;
+mod read_replica;
 mod responses;
+mod role_elevations;
+mod scheduled_reports;
+mod scheduled_tasks;
+mod shadow_results;
 #[cfg(feature = "sso")]
 mod sso_group_mappings;
 mod teams;
 mod usage;
+mod usage_anomalies;
 mod users;
+mod vector_store_syncs;
@@ -0,0 +1,195 @@
+//! Shared tests for ModelCatalogEntryRepo implementations
+
+use uuid::Uuid;
+
+use crate::{
+    catalog::{CatalogPricing, ModelCapabilities, ModelLimits},
+    db::{error::DbError, repos::ModelCatalogEntryRepo},
+    models::{CatalogEntryMetadata, CreateModelCatalogEntry},
+};
+
+fn create_input(provider: &str, model: &str) -> CreateModelCatalogEntry {
+    CreateModelCatalogEntry {
+        provider: provider.to_string(),
+        model: model.to_string(),
+        metadata: CatalogEntryMetadata {
+            capabilities: ModelCapabilities {
+                tool_call: true,
+                ..Default::default()
+            },
+            limits: ModelLimits {
+                context_length: Some(32768),
+                max_output_tokens: Some(4096),
+            },
+            pricing: Some(CatalogPricing {
+                input: 0.1,
+                output: 0.3,
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+    }
+}
+
+pub async fn create_and_get_round_trips(repo: &dyn ModelCatalogEntryRepo) {
+    let created = repo
+        .create(create_input("vllm", "llama-3.1-8b-ft"))
+        .await
+        .expect("create entry");
+    assert_eq!(created.provider, "vllm");
+    assert_eq!(created.model, "llama-3.1-8b-ft");
+
+    let fetched = repo
+        .get_by_id(created.id)
+        .await
+        .expect("get entry")
+        .expect("entry exists");
+    assert_eq!(fetched, created);
+
+    assert!(
+        repo.get_by_id(Uuid::new_v4())
+            .await
+            .expect("get missing entry")
+            .is_none()
+    );
+}
+
+pub async fn create_rejects_duplicate_model(repo: &dyn ModelCatalogEntryRepo) {
+    repo.create(create_input("vllm", "llama-3.1-8b-ft"))
+        .await
+        .expect("create entry");
+
+    let err = repo
+        .create(create_input("vllm", "llama-3.1-8b-ft"))
+        .await
+        .expect_err("duplicate should fail");
+    assert!(matches!(err, DbError::Conflict(_)));
+
+    // The same model under another provider is a separate entry
+    repo.create(create_input("ollama", "llama-3.1-8b-ft"))
+        .await
+        .expect("create entry for another provider");
+}
+
+pub async fn update_replaces_metadata(repo: &dyn ModelCatalogEntryRepo) {
+    let created = repo
+        .create(create_input("vllm", "llama-3.1-8b-ft"))
+        .await
+        .expect("create entry");
+
+    let updated = repo
+        .update(
+            created.id,
+            CatalogEntryMetadata {
+                family: Some("llama".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("update entry");
+    assert_eq!(updated.id, created.id);
+    assert_eq!(updated.created_at, created.created_at);
+    assert_eq!(updated.metadata.family.as_deref(), Some("llama"));
+    assert!(updated.metadata.pricing.is_none());
+    assert!(!updated.metadata.capabilities.tool_call);
+
+    let err = repo
+        .update(Uuid::new_v4(), CatalogEntryMetadata::default())
+        .await
+        .expect_err("missing entry should fail");
+    assert!(matches!(err, DbError::NotFound));
+}
+
+pub async fn list_orders_by_provider_and_model(repo: &dyn ModelCatalogEntryRepo) {
+    for (provider, model) in [("vllm", "b"), ("ollama", "z"), ("vllm", "a")] {
+        repo.create(create_input(provider, model))
+            .await
+            .expect("create entry");
+    }
+
+    let keys: Vec<_> = repo
+        .list()
+        .await
+        .expect("list entries")
+        .into_iter()
+        .map(|e| format!("{}/{}", e.provider, e.model))
+        .collect();
+    assert_eq!(keys, ["ollama/z", "vllm/a", "vllm/b"]);
+}
+
+pub async fn delete_removes_entry(repo: &dyn ModelCatalogEntryRepo) {
+    let created = repo
+        .create(create_input("vllm", "llama-3.1-8b-ft"))
+        .await
+        .expect("create entry");
+
+    assert!(repo.delete(created.id).await.expect("delete entry"));
+    assert!(
+        repo.get_by_id(created.id)
+            .await
+            .expect("get entry")
+            .is_none()
+    );
+    assert!(!repo.delete(created.id).await.expect("delete again"));
+}
+
+#[cfg(all(test, feature = "database-sqlite"))]
+mod sqlite_tests {
+    use crate::db::{
+        sqlite::SqliteModelCatalogEntryRepo,
+        tests::harness::{create_sqlite_pool, run_sqlite_migrations},
+    };
+
+    async fn create_repo() -> SqliteModelCatalogEntryRepo {
+        let pool = create_sqlite_pool().await;
+        run_sqlite_migrations(&pool).await;
+        SqliteModelCatalogEntryRepo::new(pool)
+    }
+
+    macro_rules! sqlite_test {
+        ($name:ident) => {
+            #[tokio::test]
+            async fn $name() {
+                let repo = create_repo().await;
+                super::$name(&repo).await;
+            }
+        };
+    }
+
+    sqlite_test!(create_and_get_round_trips);
+    sqlite_test!(create_rejects_duplicate_model);
+    sqlite_test!(update_replaces_metadata);
+    sqlite_test!(list_orders_by_provider_and_model);
+    sqlite_test!(delete_removes_entry);
+}
+
+#[cfg(all(test, feature = "database-postgres"))]
+mod postgres_tests {
+    use crate::db::{
+        postgres::PostgresModelCatalogEntryRepo,
+        tests::harness::postgres::{create_isolated_postgres_pool, run_postgres_migrations},
+    };
+
+    async fn create_repo() -> PostgresModelCatalogEntryRepo {
+        let pool = create_isolated_postgres_pool().await;
+        run_postgres_migrations(&pool).await;
+        PostgresModelCatalogEntryRepo::new(pool, None)
+    }
+
+    macro_rules! postgres_test {
+        ($name:ident) => {
+            #[tokio::test]
+            #[ignore = "Requires Docker - run with `cargo test -- --ignored`"]
+            async fn $name() {
+                let repo = create_repo().await;
+                super::$name(&repo).await;
+            }
+        };
+    }
+
+    postgres_test!(create_and_get_round_trips);
+    postgres_test!(create_rejects_duplicate_model);
+    postgres_test!(update_replaces_metadata);
+    postgres_test!(list_orders_by_provider_and_model);
+    postgres_test!(delete_removes_entry);
+}
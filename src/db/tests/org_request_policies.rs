@@ -0,0 +1,219 @@
+//! Shared tests for OrgRequestPolicyRepo implementations
+
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    db::{error::DbError, repos::OrgRequestPolicyRepo},
+    models::{CreateOrgRequestPolicy, RequestPolicyAction, UpdateOrgRequestPolicy},
+};
+
+fn policy(name: &str, priority: i32) -> CreateOrgRequestPolicy {
+    CreateOrgRequestPolicy {
+        name: name.to_string(),
+        condition: "request.model.startsWith('gpt-4')".to_string(),
+        priority,
+        ..Default::default()
+    }
+}
+
+pub async fn create_get_and_delete(repo: &dyn OrgRequestPolicyRepo, org_id: Uuid) {
+    let created = repo
+        .create(
+            org_id,
+            CreateOrgRequestPolicy {
+                action: RequestPolicyAction::Modify,
+                modifications: Some(json!({"max_tokens": 1024})),
+                ..policy("cap-tokens", 10)
+            },
+        )
+        .await
+        .expect("create policy");
+
+    let fetched = repo
+        .get_by_id(created.id)
+        .await
+        .expect("get policy")
+        .expect("policy exists");
+    assert_eq!(fetched.org_id, org_id);
+    assert_eq!(fetched.name, "cap-tokens");
+    assert_eq!(fetched.action, RequestPolicyAction::Modify);
+    assert_eq!(fetched.modifications, Some(json!({"max_tokens": 1024})));
+    assert_eq!(fetched.priority, 10);
+    assert!(fetched.enabled);
+    assert_eq!(fetched.created_at, created.created_at);
+
+    repo.delete(created.id).await.expect("delete policy");
+    assert!(repo.get_by_id(created.id).await.unwrap().is_none());
+    assert!(matches!(
+        repo.delete(created.id).await,
+        Err(DbError::NotFound)
+    ));
+}
+
+pub async fn duplicate_name_conflicts(repo: &dyn OrgRequestPolicyRepo, org_id: Uuid) {
+    repo.create(org_id, policy("block-gpt4", 0))
+        .await
+        .expect("create policy");
+    let err = repo
+        .create(org_id, policy("block-gpt4", 5))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DbError::Conflict(_)), "got {err:?}");
+}
+
+pub async fn list_orders_by_priority(repo: &dyn OrgRequestPolicyRepo, org_id: Uuid) {
+    repo.create(org_id, policy("low", -5)).await.unwrap();
+    repo.create(org_id, policy("high", 50)).await.unwrap();
+    repo.create(
+        org_id,
+        CreateOrgRequestPolicy {
+            enabled: false,
+            ..policy("disabled", 100)
+        },
+    )
+    .await
+    .unwrap();
+
+    let all = repo.list_by_org(org_id).await.expect("list policies");
+    let names: Vec<_> = all.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, ["disabled", "high", "low"]);
+
+    let enabled = repo.list_enabled_by_org(org_id).await.unwrap();
+    let names: Vec<_> = enabled.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, ["high", "low"]);
+
+    assert_eq!(repo.count_by_org(org_id).await.unwrap(), 3);
+    assert_eq!(repo.count_by_org(Uuid::new_v4()).await.unwrap(), 0);
+}
+
+pub async fn update_applies_and_clears_fields(repo: &dyn OrgRequestPolicyRepo, org_id: Uuid) {
+    let created = repo
+        .create(
+            org_id,
+            CreateOrgRequestPolicy {
+                description: Some("temporary".to_string()),
+                message: Some("Not allowed".to_string()),
+                ..policy("deny", 0)
+            },
+        )
+        .await
+        .unwrap();
+
+    let updated = repo
+        .update(
+            created.id,
+            UpdateOrgRequestPolicy {
+                description: Some(None),
+                action: Some(RequestPolicyAction::Modify),
+                modifications: Some(Some(json!({"temperature": 0.2}))),
+                message: Some(None),
+                priority: Some(7),
+                enabled: Some(false),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("update policy");
+    assert_eq!(updated.name, "deny");
+    assert!(updated.description.is_none());
+    assert!(updated.message.is_none());
+    assert_eq!(updated.action, RequestPolicyAction::Modify);
+    assert_eq!(updated.priority, 7);
+    assert!(!updated.enabled);
+
+    let fetched = repo.get_by_id(created.id).await.unwrap().unwrap();
+    assert_eq!(fetched.modifications, Some(json!({"temperature": 0.2})));
+    assert_eq!(fetched.updated_at, updated.updated_at);
+
+    assert!(matches!(
+        repo.update(Uuid::new_v4(), UpdateOrgRequestPolicy::default())
+            .await,
+        Err(DbError::NotFound)
+    ));
+}
+
+#[cfg(all(test, feature = "database-sqlite"))]
+mod sqlite_tests {
+    use uuid::Uuid;
+
+    use crate::{
+        db::{
+            repos::OrganizationRepo,
+            sqlite::{SqliteOrgRequestPolicyRepo, SqliteOrganizationRepo},
+            tests::harness::{create_sqlite_pool, run_sqlite_migrations},
+        },
+        models::CreateOrganization,
+    };
+
+    async fn create_repo() -> (SqliteOrgRequestPolicyRepo, Uuid) {
+        let pool = create_sqlite_pool().await;
+        run_sqlite_migrations(&pool).await;
+        let org = SqliteOrganizationRepo::new(pool.clone())
+            .create(CreateOrganization {
+                slug: "acme".to_string(),
+                name: "Acme".to_string(),
+            })
+            .await
+            .expect("create org");
+        (SqliteOrgRequestPolicyRepo::new(pool), org.id)
+    }
+
+    macro_rules! sqlite_test {
+        ($name:ident) => {
+            #[tokio::test]
+            async fn $name() {
+                let (repo, org_id) = create_repo().await;
+                super::$name(&repo, org_id).await;
+            }
+        };
+    }
+
+    sqlite_test!(create_get_and_delete);
+    sqlite_test!(duplicate_name_conflicts);
+    sqlite_test!(list_orders_by_priority);
+    sqlite_test!(update_applies_and_clears_fields);
+}
+
+#[cfg(all(test, feature = "database-postgres"))]
+mod postgres_tests {
+    use uuid::Uuid;
+
+    use crate::{
+        db::{
+            postgres::{PostgresOrgRequestPolicyRepo, PostgresOrganizationRepo},
+            repos::OrganizationRepo,
+            tests::harness::postgres::{create_isolated_postgres_pool, run_postgres_migrations},
+        },
+        models::CreateOrganization,
+    };
+
+    async fn create_repo() -> (PostgresOrgRequestPolicyRepo, Uuid) {
+        let pool = create_isolated_postgres_pool().await;
+        run_postgres_migrations(&pool).await;
+        let org = PostgresOrganizationRepo::new(pool.clone(), None)
+            .create(CreateOrganization {
+                slug: "acme".to_string(),
+                name: "Acme".to_string(),
+            })
+            .await
+            .expect("create org");
+        (PostgresOrgRequestPolicyRepo::new(pool, None), org.id)
+    }
+
+    macro_rules! postgres_test {
+        ($name:ident) => {
+            #[tokio::test]
+            #[ignore = "Requires Docker - run with `cargo test -- --ignored`"]
+            async fn $name() {
+                let (repo, org_id) = create_repo().await;
+                super::$name(&repo, org_id).await;
+            }
+        };
+    }
+
+    postgres_test!(create_get_and_delete);
+    postgres_test!(duplicate_name_conflicts);
+    postgres_test!(list_orders_by_priority);
+    postgres_test!(update_applies_and_clears_fields);
+}
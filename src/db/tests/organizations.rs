@@ -3,14 +3,22 @@
 //! Tests are written as async functions that take `&dyn OrganizationRepo`,
 //! allowing the same test logic to run against both SQLite and PostgreSQL.
 
+use std::collections::BTreeMap;
+
 use uuid::Uuid;
 
 use crate::{
+    config::DataResidencyPolicy,
     db::{
         error::DbError,
         repos::{ListParams, OrganizationRepo},
     },
-    models::{CreateOrganization, UpdateOrganization},
+    models::{
+        ConversationSummarySettings, CreateOrganization, DegradationLadder, DegradationTrigger,
+        GovernanceViolationAction, LabelSelector, ModelAccessPolicy, ModelDegradationPolicy,
+        OrgEntitlements, OrgNetworkPolicy, ParameterGovernance, ParameterGovernanceRule,
+        RequestDefaults, UpdateOrganization,
+    },
 };
 
 // ============================================================================
@@ -196,6 +204,9 @@ pub async fn test_update_name(repo: &dyn OrganizationRepo) {
             created.id,
             UpdateOrganization {
                 name: Some("Updated Name".to_string()),
+                data_residency: None,
+                request_defaults: None,
+                conversation_summaries: None,
             },
         )
         .await
@@ -214,19 +225,456 @@ pub async fn test_update_no_changes(repo: &dyn OrganizationRepo) {
         .expect("Failed to create org");
 
     let result = repo
-        .update(created.id, UpdateOrganization { name: None })
+        .update(
+            created.id,
+            UpdateOrganization {
+                name: None,
+                data_residency: None,
+                request_defaults: None,
+                conversation_summaries: None,
+            },
+        )
         .await
         .expect("Failed to update org");
 
     assert_eq!(result.name, "Original");
 }
 
+pub async fn test_update_data_residency(repo: &dyn OrganizationRepo) {
+    let created = repo
+        .create(create_org_input("residency", "Residency Org"))
+        .await
+        .expect("Failed to create org");
+    assert!(created.data_residency.is_none());
+
+    let policy = DataResidencyPolicy {
+        allowed_regions: vec!["eu".to_string()],
+    };
+    let updated = repo
+        .update(
+            created.id,
+            UpdateOrganization {
+                name: None,
+                data_residency: Some(Some(policy.clone())),
+                request_defaults: None,
+                conversation_summaries: None,
+            },
+        )
+        .await
+        .expect("Failed to set data residency");
+    assert_eq!(updated.name, "Residency Org");
+    assert_eq!(updated.data_residency, Some(policy.clone()));
+
+    let fetched = repo
+        .get_by_slug("residency")
+        .await
+        .expect("Failed to get org")
+        .expect("Org should exist");
+    assert_eq!(fetched.data_residency, Some(policy));
+
+    let cleared = repo
+        .update(
+            created.id,
+            UpdateOrganization {
+                name: None,
+                data_residency: Some(None),
+                request_defaults: None,
+                conversation_summaries: None,
+            },
+        )
+        .await
+        .expect("Failed to clear data residency");
+    assert!(cleared.data_residency.is_none());
+}
+
+pub async fn test_update_request_defaults(repo: &dyn OrganizationRepo) {
+    let created = repo
+        .create(create_org_input("defaults", "Defaults Org"))
+        .await
+        .expect("Failed to create org");
+    assert!(created.request_defaults.is_none());
+
+    let defaults = RequestDefaults {
+        model: Some("openai/gpt-4o-mini".to_string()),
+        temperature: Some(0.3),
+        system_prompt: Some("You are a helpful assistant.".to_string()),
+        structured_output_repairs: None,
+    };
+    let updated = repo
+        .update(
+            created.id,
+            UpdateOrganization {
+                name: None,
+                data_residency: None,
+                request_defaults: Some(Some(defaults.clone())),
+                conversation_summaries: None,
+            },
+        )
+        .await
+        .expect("Failed to set request defaults");
+    assert_eq!(updated.request_defaults, Some(defaults.clone()));
+    assert!(updated.data_residency.is_none());
+
+    let fetched = repo
+        .get_by_id(created.id)
+        .await
+        .expect("Failed to get org")
+        .expect("Org should exist");
+    assert_eq!(fetched.request_defaults, Some(defaults));
+
+    let cleared = repo
+        .update(
+            created.id,
+            UpdateOrganization {
+                name: None,
+                data_residency: None,
+                request_defaults: Some(None),
+                conversation_summaries: None,
+            },
+        )
+        .await
+        .expect("Failed to clear request defaults");
+    assert!(cleared.request_defaults.is_none());
+}
+
+pub async fn test_update_conversation_summaries(repo: &dyn OrganizationRepo) {
+    let created = repo
+        .create(create_org_input("summaries", "Summaries Org"))
+        .await
+        .expect("Failed to create org");
+    assert!(created.conversation_summaries.is_none());
+
+    let settings = ConversationSummarySettings {
+        enabled: false,
+        model: Some("gpt-4o-mini".to_string()),
+    };
+    let updated = repo
+        .update(
+            created.id,
+            UpdateOrganization {
+                name: None,
+                data_residency: None,
+                request_defaults: None,
+                conversation_summaries: Some(Some(settings.clone())),
+            },
+        )
+        .await
+        .expect("Failed to set conversation summary settings");
+    assert_eq!(updated.conversation_summaries, Some(settings.clone()));
+
+    let fetched = repo
+        .get_by_slug("summaries")
+        .await
+        .expect("Failed to get org")
+        .expect("Org should exist");
+    assert_eq!(fetched.conversation_summaries, Some(settings));
+
+    let cleared = repo
+        .update(
+            created.id,
+            UpdateOrganization {
+                name: None,
+                data_residency: None,
+                request_defaults: None,
+                conversation_summaries: Some(None),
+            },
+        )
+        .await
+        .expect("Failed to clear conversation summary settings");
+    assert!(cleared.conversation_summaries.is_none());
+}
+
+pub async fn test_set_network_policy(repo: &dyn OrganizationRepo) {
+    let created = repo
+        .create(create_org_input("network", "Network Org"))
+        .await
+        .expect("Failed to create org");
+    assert!(created.network_policy.is_none());
+
+    let policy = OrgNetworkPolicy {
+        ip_allowlist: vec!["10.0.0.0/8".to_string(), "203.0.113.7".to_string()],
+    };
+    let updated = repo
+        .set_network_policy(created.id, Some(&policy))
+        .await
+        .expect("Failed to set network policy");
+    assert_eq!(updated.network_policy, Some(policy.clone()));
+
+    let fetched = repo
+        .get_by_slug("network")
+        .await
+        .expect("Failed to get org")
+        .expect("Org should exist");
+    assert_eq!(fetched.network_policy, Some(policy));
+
+    let cleared = repo
+        .set_network_policy(created.id, None)
+        .await
+        .expect("Failed to clear network policy");
+    assert!(cleared.network_policy.is_none());
+
+    let result = repo.set_network_policy(Uuid::new_v4(), None).await;
+    assert!(matches!(result, Err(DbError::NotFound)));
+}
+
+pub async fn test_set_parameter_governance(repo: &dyn OrganizationRepo) {
+    let created = repo
+        .create(create_org_input("governed", "Governed Org"))
+        .await
+        .expect("Failed to create org");
+    assert!(created.parameter_governance.is_none());
+
+    let governance = ParameterGovernance {
+        rules: vec![ParameterGovernanceRule {
+            max_tokens: Some(4096),
+            min_temperature: Some(0.0),
+            max_temperature: Some(1.0),
+            default_system_prompt: Some("Follow the acceptable use policy.".to_string()),
+            on_violation: GovernanceViolationAction::Clamp,
+            ..Default::default()
+        }],
+    };
+    let updated = repo
+        .set_parameter_governance(created.id, Some(&governance))
+        .await
+        .expect("Failed to set parameter governance");
+    assert_eq!(updated.parameter_governance, Some(governance.clone()));
+
+    let fetched = repo
+        .get_by_slug("governed")
+        .await
+        .expect("Failed to get org")
+        .expect("Org should exist");
+    assert_eq!(fetched.parameter_governance, Some(governance));
+
+    let cleared = repo
+        .set_parameter_governance(created.id, None)
+        .await
+        .expect("Failed to clear parameter governance");
+    assert!(cleared.parameter_governance.is_none());
+
+    let result = repo.set_parameter_governance(Uuid::new_v4(), None).await;
+    assert!(matches!(result, Err(DbError::NotFound)));
+}
+
+pub async fn test_set_model_access(repo: &dyn OrganizationRepo) {
+    let created = repo
+        .create(create_org_input("restricted", "Restricted Org"))
+        .await
+        .expect("Failed to create org");
+    assert!(created.model_access.is_none());
+
+    let policy = ModelAccessPolicy {
+        allowed_models: vec!["gpt-4*".to_string(), "claude-sonnet-4".to_string()],
+        denied_providers: vec!["bedrock".to_string()],
+        ..Default::default()
+    };
+    let updated = repo
+        .set_model_access(created.id, Some(&policy))
+        .await
+        .expect("Failed to set model access");
+    assert_eq!(updated.model_access, Some(policy.clone()));
+
+    let fetched = repo
+        .get_by_slug("restricted")
+        .await
+        .expect("Failed to get org")
+        .expect("Org should exist");
+    assert_eq!(fetched.model_access, Some(policy));
+
+    let cleared = repo
+        .set_model_access(created.id, None)
+        .await
+        .expect("Failed to clear model access");
+    assert!(cleared.model_access.is_none());
+
+    let result = repo.set_model_access(Uuid::new_v4(), None).await;
+    assert!(matches!(result, Err(DbError::NotFound)));
+}
+
+pub async fn test_set_model_degradation(repo: &dyn OrganizationRepo) {
+    let created = repo
+        .create(create_org_input("degrading", "Degrading Org"))
+        .await
+        .expect("Failed to create org");
+    assert!(created.model_degradation.is_none());
+
+    let policy = ModelDegradationPolicy {
+        ladders: vec![DegradationLadder {
+            model: "openai/gpt-4o".to_string(),
+            downgrades: vec!["openai/gpt-4o-mini".to_string()],
+        }],
+        triggers: vec![DegradationTrigger::RateLimited],
+        api_keys: vec![Uuid::new_v4()],
+    };
+    let updated = repo
+        .set_model_degradation(created.id, Some(&policy))
+        .await
+        .expect("Failed to set model degradation");
+    assert_eq!(updated.model_degradation, Some(policy.clone()));
+
+    let fetched = repo
+        .get_by_slug("degrading")
+        .await
+        .expect("Failed to get org")
+        .expect("Org should exist");
+    assert_eq!(fetched.model_degradation, Some(policy));
+
+    let cleared = repo
+        .set_model_degradation(created.id, None)
+        .await
+        .expect("Failed to clear model degradation");
+    assert!(cleared.model_degradation.is_none());
+
+    let result = repo.set_model_degradation(Uuid::new_v4(), None).await;
+    assert!(matches!(result, Err(DbError::NotFound)));
+}
+
+pub async fn test_set_entitlements(repo: &dyn OrganizationRepo) {
+    let created = repo
+        .create(create_org_input("entitled", "Entitled Org"))
+        .await
+        .expect("Failed to create org");
+    assert!(created.entitlements.is_none());
+
+    let entitlements = OrgEntitlements {
+        plan: Some("team".to_string()),
+        batch_api: Some(true),
+        max_api_keys: Some(250),
+        ..Default::default()
+    };
+    let updated = repo
+        .set_entitlements(created.id, Some(&entitlements))
+        .await
+        .expect("Failed to set entitlements");
+    assert_eq!(updated.entitlements, Some(entitlements.clone()));
+
+    let fetched = repo
+        .get_by_slug("entitled")
+        .await
+        .expect("Failed to get org")
+        .expect("Org should exist");
+    assert_eq!(fetched.entitlements, Some(entitlements));
+
+    let cleared = repo
+        .set_entitlements(created.id, None)
+        .await
+        .expect("Failed to clear entitlements");
+    assert!(cleared.entitlements.is_none());
+
+    let result = repo.set_entitlements(Uuid::new_v4(), None).await;
+    assert!(matches!(result, Err(DbError::NotFound)));
+}
+
+pub async fn test_set_labels(repo: &dyn OrganizationRepo) {
+    let created = repo
+        .create(create_org_input("labelled", "Labelled Org"))
+        .await
+        .expect("Failed to create org");
+    assert!(created.labels.is_empty());
+
+    let labels = BTreeMap::from([
+        ("env".to_string(), "prod".to_string()),
+        ("team".to_string(), "search".to_string()),
+    ]);
+    let updated = repo
+        .set_labels(created.id, &labels)
+        .await
+        .expect("Failed to set labels");
+    assert_eq!(updated.labels, labels);
+
+    let fetched = repo
+        .get_by_slug("labelled")
+        .await
+        .expect("Failed to get org")
+        .expect("Org should exist");
+    assert_eq!(fetched.labels, labels);
+
+    let cleared = repo
+        .set_labels(created.id, &BTreeMap::new())
+        .await
+        .expect("Failed to clear labels");
+    assert!(cleared.labels.is_empty());
+
+    let result = repo.set_labels(Uuid::new_v4(), &labels).await;
+    assert!(matches!(result, Err(DbError::NotFound)));
+}
+
+pub async fn test_list_by_labels(repo: &dyn OrganizationRepo) {
+    for (slug, env) in [("org-a", "prod"), ("org-b", "prod"), ("org-c", "dev")] {
+        let org = repo
+            .create(create_org_input(slug, slug))
+            .await
+            .expect("Failed to create org");
+        let labels = BTreeMap::from([
+            ("env".to_string(), env.to_string()),
+            ("tier".to_string(), "web".to_string()),
+        ]);
+        repo.set_labels(org.id, &labels)
+            .await
+            .expect("Failed to set labels");
+    }
+    repo.create(create_org_input("org-d", "Unlabelled"))
+        .await
+        .expect("Failed to create org");
+
+    let list = |selector: &str| ListParams {
+        labels: LabelSelector::parse(selector).unwrap(),
+        ..Default::default()
+    };
+
+    let prod = repo.list(list("env=prod")).await.expect("Failed to list");
+    let mut slugs: Vec<_> = prod.items.iter().map(|o| o.slug.as_str()).collect();
+    slugs.sort();
+    assert_eq!(slugs, ["org-a", "org-b"]);
+
+    let dev_web = repo
+        .list(list("env=dev,tier=web"))
+        .await
+        .expect("Failed to list");
+    assert_eq!(dev_web.items.len(), 1);
+    assert_eq!(dev_web.items[0].slug, "org-c");
+
+    let none = repo
+        .list(list("env=prod,tier=db"))
+        .await
+        .expect("Failed to list");
+    assert!(none.items.is_empty());
+
+    let all = repo.list(list("")).await.expect("Failed to list");
+    assert_eq!(all.items.len(), 4);
+
+    // The selector applies to every page
+    let page1 = repo
+        .list(ListParams {
+            limit: Some(1),
+            ..list("env=prod")
+        })
+        .await
+        .expect("Failed to list page 1");
+    let page2 = repo
+        .list(ListParams {
+            limit: Some(1),
+            cursor: page1.cursors.next.clone(),
+            ..list("env=prod")
+        })
+        .await
+        .expect("Failed to list page 2");
+    assert!(page1.has_more);
+    assert!(!page2.has_more);
+    assert_eq!(page2.items.len(), 1);
+    assert_ne!(page1.items[0].id, page2.items[0].id);
+}
+
 pub async fn test_update_not_found(repo: &dyn OrganizationRepo) {
     let result = repo
         .update(
             Uuid::new_v4(),
             UpdateOrganization {
                 name: Some("New Name".to_string()),
+                data_residency: None,
+                request_defaults: None,
+                conversation_summaries: None,
             },
         )
         .await;
@@ -350,6 +798,9 @@ pub async fn test_update_deleted_org_fails(repo: &dyn OrganizationRepo) {
             created.id,
             UpdateOrganization {
                 name: Some("New Name".to_string()),
+                data_residency: None,
+                request_defaults: None,
+                conversation_summaries: None,
             },
         )
         .await;
@@ -453,6 +904,66 @@ mod sqlite_tests {
         test_update_no_changes(&repo).await;
     }
 
+    #[tokio::test]
+    async fn sqlite_update_data_residency() {
+        let repo = create_repo().await;
+        test_update_data_residency(&repo).await;
+    }
+
+    #[tokio::test]
+    async fn sqlite_update_request_defaults() {
+        let repo = create_repo().await;
+        test_update_request_defaults(&repo).await;
+    }
+
+    #[tokio::test]
+    async fn sqlite_update_conversation_summaries() {
+        let repo = create_repo().await;
+        test_update_conversation_summaries(&repo).await;
+    }
+
+    #[tokio::test]
+    async fn sqlite_set_network_policy() {
+        let repo = create_repo().await;
+        test_set_network_policy(&repo).await;
+    }
+
+    #[tokio::test]
+    async fn sqlite_set_parameter_governance() {
+        let repo = create_repo().await;
+        test_set_parameter_governance(&repo).await;
+    }
+
+    #[tokio::test]
+    async fn sqlite_set_model_access() {
+        let repo = create_repo().await;
+        test_set_model_access(&repo).await;
+    }
+
+    #[tokio::test]
+    async fn sqlite_set_model_degradation() {
+        let repo = create_repo().await;
+        test_set_model_degradation(&repo).await;
+    }
+
+    #[tokio::test]
+    async fn sqlite_set_entitlements() {
+        let repo = create_repo().await;
+        test_set_entitlements(&repo).await;
+    }
+
+    #[tokio::test]
+    async fn sqlite_set_labels() {
+        let repo = create_repo().await;
+        test_set_labels(&repo).await;
+    }
+
+    #[tokio::test]
+    async fn sqlite_list_by_labels() {
+        let repo = create_repo().await;
+        test_list_by_labels(&repo).await;
+    }
+
     #[tokio::test]
     async fn sqlite_update_not_found() {
         let repo = create_repo().await;
@@ -539,6 +1050,16 @@ mod postgres_tests {
     postgres_test!(test_count_with_orgs);
     postgres_test!(test_update_name);
     postgres_test!(test_update_no_changes);
+    postgres_test!(test_update_data_residency);
+    postgres_test!(test_update_request_defaults);
+    postgres_test!(test_update_conversation_summaries);
+    postgres_test!(test_set_network_policy);
+    postgres_test!(test_set_parameter_governance);
+    postgres_test!(test_set_model_access);
+    postgres_test!(test_set_model_degradation);
+    postgres_test!(test_set_entitlements);
+    postgres_test!(test_set_labels);
+    postgres_test!(test_list_by_labels);
     postgres_test!(test_update_not_found);
     postgres_test!(test_delete);
     postgres_test!(test_delete_not_found);
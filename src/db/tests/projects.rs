@@ -10,7 +10,10 @@ use crate::{
         error::DbError,
         repos::{ListParams, OrganizationRepo, ProjectRepo},
     },
-    models::{CreateOrganization, CreateProject, UpdateProject},
+    models::{
+        CreateOrganization, CreateProject, GovernanceViolationAction, ModelAccessPolicy,
+        ParameterGovernance, ParameterGovernanceRule, RequestDefaults, UpdateProject,
+    },
 };
 
 // ============================================================================
@@ -360,6 +363,7 @@ pub async fn test_update_name(ctx: &ProjectTestContext<'_>) {
             UpdateProject {
                 name: Some("Updated Name".to_string()),
                 team_id: None,
+                request_defaults: None,
             },
         )
         .await
@@ -387,6 +391,7 @@ pub async fn test_update_no_changes(ctx: &ProjectTestContext<'_>) {
             UpdateProject {
                 name: None,
                 team_id: None,
+                request_defaults: None,
             },
         )
         .await
@@ -395,6 +400,152 @@ pub async fn test_update_no_changes(ctx: &ProjectTestContext<'_>) {
     assert_eq!(result.name, "Original");
 }
 
+pub async fn test_update_request_defaults(ctx: &ProjectTestContext<'_>) {
+    let org_id = ctx.create_test_org("test-org").await;
+
+    let created = ctx
+        .project_repo
+        .create(org_id, create_project_input("defaults", "Defaults"))
+        .await
+        .expect("Failed to create project");
+    assert!(created.request_defaults.is_none());
+
+    let defaults = RequestDefaults {
+        model: Some("anthropic/claude-sonnet-4".to_string()),
+        temperature: None,
+        system_prompt: Some("Answer in French.".to_string()),
+        structured_output_repairs: None,
+    };
+    let updated = ctx
+        .project_repo
+        .update(
+            created.id,
+            UpdateProject {
+                name: None,
+                team_id: None,
+                request_defaults: Some(Some(defaults.clone())),
+            },
+        )
+        .await
+        .expect("Failed to set request defaults");
+    assert_eq!(updated.name, "Defaults");
+    assert_eq!(updated.request_defaults, Some(defaults.clone()));
+
+    let fetched = ctx
+        .project_repo
+        .get_by_slug(org_id, "defaults")
+        .await
+        .expect("Failed to get project")
+        .expect("Project should exist");
+    assert_eq!(fetched.request_defaults, Some(defaults));
+
+    let cleared = ctx
+        .project_repo
+        .update(
+            created.id,
+            UpdateProject {
+                name: None,
+                team_id: None,
+                request_defaults: Some(None),
+            },
+        )
+        .await
+        .expect("Failed to clear request defaults");
+    assert!(cleared.request_defaults.is_none());
+}
+
+pub async fn test_set_parameter_governance(ctx: &ProjectTestContext<'_>) {
+    let org_id = ctx.create_test_org("test-org").await;
+
+    let created = ctx
+        .project_repo
+        .create(org_id, create_project_input("governed", "Governed"))
+        .await
+        .expect("Failed to create project");
+    assert!(created.parameter_governance.is_none());
+
+    let governance = ParameterGovernance {
+        rules: vec![ParameterGovernanceRule {
+            models: vec!["gpt-4*".to_string()],
+            max_tokens: Some(2048),
+            forbidden_params: vec!["logit_bias".to_string()],
+            on_violation: GovernanceViolationAction::Reject,
+            ..Default::default()
+        }],
+    };
+    let updated = ctx
+        .project_repo
+        .set_parameter_governance(created.id, Some(&governance))
+        .await
+        .expect("Failed to set parameter governance");
+    assert_eq!(updated.parameter_governance, Some(governance.clone()));
+
+    let fetched = ctx
+        .project_repo
+        .get_by_slug(org_id, "governed")
+        .await
+        .expect("Failed to get project")
+        .expect("Project should exist");
+    assert_eq!(fetched.parameter_governance, Some(governance));
+
+    let cleared = ctx
+        .project_repo
+        .set_parameter_governance(created.id, None)
+        .await
+        .expect("Failed to clear parameter governance");
+    assert!(cleared.parameter_governance.is_none());
+
+    let result = ctx
+        .project_repo
+        .set_parameter_governance(Uuid::new_v4(), None)
+        .await;
+    assert!(matches!(result, Err(DbError::NotFound)));
+}
+
+pub async fn test_set_model_access(ctx: &ProjectTestContext<'_>) {
+    let org_id = ctx.create_test_org("test-org").await;
+
+    let created = ctx
+        .project_repo
+        .create(org_id, create_project_input("restricted", "Restricted"))
+        .await
+        .expect("Failed to create project");
+    assert!(created.model_access.is_none());
+
+    let policy = ModelAccessPolicy {
+        denied_models: vec!["gpt-4-32k".to_string()],
+        allowed_providers: vec!["openai".to_string()],
+        ..Default::default()
+    };
+    let updated = ctx
+        .project_repo
+        .set_model_access(created.id, Some(&policy))
+        .await
+        .expect("Failed to set model access");
+    assert_eq!(updated.model_access, Some(policy.clone()));
+
+    let fetched = ctx
+        .project_repo
+        .get_by_slug(org_id, "restricted")
+        .await
+        .expect("Failed to get project")
+        .expect("Project should exist");
+    assert_eq!(fetched.model_access, Some(policy));
+
+    let cleared = ctx
+        .project_repo
+        .set_model_access(created.id, None)
+        .await
+        .expect("Failed to clear model access");
+    assert!(cleared.model_access.is_none());
+
+    let result = ctx
+        .project_repo
+        .set_model_access(Uuid::new_v4(), None)
+        .await;
+    assert!(matches!(result, Err(DbError::NotFound)));
+}
+
 pub async fn test_update_not_found(ctx: &ProjectTestContext<'_>) {
     let result = ctx
         .project_repo
@@ -403,6 +554,7 @@ pub async fn test_update_not_found(ctx: &ProjectTestContext<'_>) {
             UpdateProject {
                 name: Some("New Name".to_string()),
                 team_id: None,
+                request_defaults: None,
             },
         )
         .await;
@@ -588,6 +740,7 @@ pub async fn test_update_deleted_project_fails(ctx: &ProjectTestContext<'_>) {
             UpdateProject {
                 name: Some("New Name".to_string()),
                 team_id: None,
+                request_defaults: None,
             },
         )
         .await;
@@ -646,6 +799,9 @@ mod sqlite_tests {
     sqlite_test!(test_count_by_org_with_projects);
     sqlite_test!(test_update_name);
     sqlite_test!(test_update_no_changes);
+    sqlite_test!(test_update_request_defaults);
+    sqlite_test!(test_set_parameter_governance);
+    sqlite_test!(test_set_model_access);
     sqlite_test!(test_update_not_found);
     sqlite_test!(test_delete);
     sqlite_test!(test_delete_not_found);
@@ -702,6 +858,9 @@ mod postgres_tests {
     postgres_test!(test_count_by_org_with_projects);
     postgres_test!(test_update_name);
     postgres_test!(test_update_no_changes);
+    postgres_test!(test_update_request_defaults);
+    postgres_test!(test_set_parameter_governance);
+    postgres_test!(test_set_model_access);
     postgres_test!(test_update_not_found);
     postgres_test!(test_delete);
     postgres_test!(test_delete_not_found);
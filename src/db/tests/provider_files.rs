@@ -0,0 +1,139 @@
+//! Shared tests for ProviderFileRepo implementations
+
+use uuid::Uuid;
+
+use crate::db::{error::DbError, repos::ProviderFileRepo};
+
+pub async fn create_and_get_round_trips(repo: &dyn ProviderFileRepo) {
+    let file_id = Uuid::new_v4();
+    let created = repo
+        .create(file_id, "openai", "file-abc123")
+        .await
+        .expect("create provider file");
+    assert_eq!(created.file_id, file_id);
+    assert_eq!(created.provider_file_id, "file-abc123");
+
+    let fetched = repo
+        .get(file_id, "openai")
+        .await
+        .expect("get provider file")
+        .expect("provider file exists");
+    assert_eq!(fetched, created);
+
+    assert!(
+        repo.get(file_id, "azure")
+            .await
+            .expect("get other provider")
+            .is_none()
+    );
+}
+
+pub async fn create_rejects_second_copy_at_provider(repo: &dyn ProviderFileRepo) {
+    let file_id = Uuid::new_v4();
+    repo.create(file_id, "openai", "file-abc123")
+        .await
+        .expect("create provider file");
+
+    let err = repo
+        .create(file_id, "openai", "file-def456")
+        .await
+        .expect_err("duplicate copy should fail");
+    assert!(matches!(err, DbError::Conflict(_)));
+
+    repo.create(file_id, "azure", "assistant-file-1")
+        .await
+        .expect("copy at another provider");
+}
+
+pub async fn list_and_delete_copies(repo: &dyn ProviderFileRepo) {
+    let file_id = Uuid::new_v4();
+    let first = repo
+        .create(file_id, "openai", "file-abc123")
+        .await
+        .expect("create first copy");
+    let second = repo
+        .create(file_id, "azure", "assistant-file-1")
+        .await
+        .expect("create second copy");
+    repo.create(Uuid::new_v4(), "openai", "file-other")
+        .await
+        .expect("create other file's copy");
+
+    let ids: Vec<_> = repo
+        .list_by_file(file_id)
+        .await
+        .expect("list copies")
+        .into_iter()
+        .map(|c| c.id)
+        .collect();
+    assert_eq!(ids.len(), 2);
+    assert!(ids.contains(&first.id) && ids.contains(&second.id));
+
+    repo.delete(first.id).await.expect("delete copy");
+    let remaining = repo.list_by_file(file_id).await.expect("list remaining");
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].id, second.id);
+
+    let err = repo
+        .delete(first.id)
+        .await
+        .expect_err("deleting twice should fail");
+    assert!(matches!(err, DbError::NotFound));
+}
+
+#[cfg(all(test, feature = "database-sqlite"))]
+mod sqlite_tests {
+    use crate::db::{
+        sqlite::SqliteProviderFileRepo,
+        tests::harness::{create_sqlite_pool, run_sqlite_migrations},
+    };
+
+    async fn create_repo() -> SqliteProviderFileRepo {
+        let pool = create_sqlite_pool().await;
+        run_sqlite_migrations(&pool).await;
+        SqliteProviderFileRepo::new(pool)
+    }
+
+    macro_rules! sqlite_test {
+        ($name:ident) => {
+            #[tokio::test]
+            async fn $name() {
+                let repo = create_repo().await;
+                super::$name(&repo).await;
+            }
+        };
+    }
+
+    sqlite_test!(create_and_get_round_trips);
+    sqlite_test!(create_rejects_second_copy_at_provider);
+    sqlite_test!(list_and_delete_copies);
+}
+
+#[cfg(all(test, feature = "database-postgres"))]
+mod postgres_tests {
+    use crate::db::{
+        postgres::PostgresProviderFileRepo,
+        tests::harness::postgres::{create_isolated_postgres_pool, run_postgres_migrations},
+    };
+
+    async fn create_repo() -> PostgresProviderFileRepo {
+        let pool = create_isolated_postgres_pool().await;
+        run_postgres_migrations(&pool).await;
+        PostgresProviderFileRepo::new(pool, None)
+    }
+
+    macro_rules! postgres_test {
+        ($name:ident) => {
+            #[tokio::test]
+            #[ignore = "Requires Docker - run with `cargo test -- --ignored`"]
+            async fn $name() {
+                let repo = create_repo().await;
+                super::$name(&repo).await;
+            }
+        };
+    }
+
+    postgres_test!(create_and_get_round_trips);
+    postgres_test!(create_rejects_second_copy_at_provider);
+    postgres_test!(list_and_delete_copies);
+}
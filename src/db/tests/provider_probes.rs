@@ -0,0 +1,184 @@
+//! Shared tests for ProviderProbeRepo implementations
+
+use chrono::{Duration, DurationRound, Utc};
+
+use crate::{db::repos::ProviderProbeRepo, models::CreateProviderProbeResult};
+
+fn probe_input(
+    provider: &str,
+    model: &str,
+    latency_ms: i64,
+    correct: bool,
+) -> CreateProviderProbeResult {
+    CreateProviderProbeResult {
+        provider: provider.to_string(),
+        model: model.to_string(),
+        success: true,
+        correct,
+        latency_ms,
+        input_tokens: Some(12),
+        output_tokens: Some(2),
+        error: None,
+    }
+}
+
+pub async fn create_and_history(repo: &dyn ProviderProbeRepo) {
+    let created = repo
+        .create(probe_input("openai", "gpt-4o-mini", 200, true))
+        .await
+        .expect("create probe");
+    assert!(created.success);
+    assert_eq!(created.latency_ms, 200);
+
+    repo.create(probe_input("openai", "gpt-4o-mini", 400, false))
+        .await
+        .expect("create probe");
+    repo.create(probe_input("openai", "gpt-4o", 900, true))
+        .await
+        .expect("create probe");
+    repo.create(CreateProviderProbeResult {
+        success: false,
+        correct: false,
+        input_tokens: None,
+        output_tokens: None,
+        error: Some("HTTP 503".to_string()),
+        ..probe_input("openai", "gpt-4o-mini", 5_000, false)
+    })
+    .await
+    .expect("create failed probe");
+    repo.create(probe_input("anthropic", "claude-haiku", 300, true))
+        .await
+        .expect("create other provider probe");
+
+    let now = Utc::now();
+    let history = repo
+        .history(
+            "openai",
+            now - Duration::hours(1),
+            now + Duration::hours(1),
+            86_400,
+        )
+        .await
+        .expect("probe history");
+    assert_eq!(history.len(), 2);
+
+    let bucket_start = now.duration_trunc(Duration::days(1)).unwrap();
+    let four_o = &history[0];
+    assert_eq!(four_o.model, "gpt-4o");
+    assert_eq!(four_o.bucket_start, bucket_start);
+    assert_eq!(four_o.probe_count, 1);
+
+    let mini = &history[1];
+    assert_eq!(mini.model, "gpt-4o-mini");
+    assert_eq!(mini.probe_count, 3);
+    assert_eq!(mini.success_count, 2);
+    assert_eq!(mini.correct_count, 1);
+    // The failed probe doesn't count towards latency or tokens
+    assert_eq!(mini.avg_latency_ms, Some(300.0));
+    assert_eq!(mini.max_latency_ms, Some(400));
+    assert_eq!(mini.avg_output_tokens, Some(2.0));
+
+    let empty = repo
+        .history(
+            "openai",
+            now - Duration::days(3),
+            now - Duration::days(2),
+            3_600,
+        )
+        .await
+        .expect("empty history");
+    assert!(empty.is_empty());
+}
+
+pub async fn delete_before(repo: &dyn ProviderProbeRepo) {
+    repo.create(probe_input("openai", "gpt-4o-mini", 200, true))
+        .await
+        .expect("create probe");
+    repo.create(probe_input("anthropic", "claude-haiku", 300, true))
+        .await
+        .expect("create probe");
+
+    let now = Utc::now();
+    assert_eq!(
+        repo.delete_before("openai", now - Duration::hours(1))
+            .await
+            .unwrap(),
+        0
+    );
+    assert_eq!(
+        repo.delete_before("openai", now + Duration::seconds(1))
+            .await
+            .unwrap(),
+        1
+    );
+
+    let range = (now - Duration::hours(1), now + Duration::hours(1));
+    assert!(
+        repo.history("openai", range.0, range.1, 3_600)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(
+        repo.history("anthropic", range.0, range.1, 3_600)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[cfg(all(test, feature = "database-sqlite"))]
+mod sqlite_tests {
+    use crate::db::{
+        sqlite::SqliteProviderProbeRepo,
+        tests::harness::{create_sqlite_pool, run_sqlite_migrations},
+    };
+
+    async fn create_repo() -> SqliteProviderProbeRepo {
+        let pool = create_sqlite_pool().await;
+        run_sqlite_migrations(&pool).await;
+        SqliteProviderProbeRepo::new(pool)
+    }
+
+    macro_rules! sqlite_test {
+        ($name:ident) => {
+            #[tokio::test]
+            async fn $name() {
+                let repo = create_repo().await;
+                super::$name(&repo).await;
+            }
+        };
+    }
+
+    sqlite_test!(create_and_history);
+    sqlite_test!(delete_before);
+}
+
+#[cfg(all(test, feature = "database-postgres"))]
+mod postgres_tests {
+    use crate::db::{
+        postgres::PostgresProviderProbeRepo,
+        tests::harness::postgres::{create_isolated_postgres_pool, run_postgres_migrations},
+    };
+
+    async fn create_repo() -> PostgresProviderProbeRepo {
+        let pool = create_isolated_postgres_pool().await;
+        run_postgres_migrations(&pool).await;
+        PostgresProviderProbeRepo::new(pool, None)
+    }
+
+    macro_rules! postgres_test {
+        ($name:ident) => {
+            #[tokio::test]
+            #[ignore = "Requires Docker - run with `cargo test -- --ignored`"]
+            async fn $name() {
+                let repo = create_repo().await;
+                super::$name(&repo).await;
+            }
+        };
+    }
+
+    postgres_test!(create_and_history);
+    postgres_test!(delete_before);
+}
@@ -0,0 +1,158 @@
+//! Shared tests for ProviderSettingsRepo implementations
+
+use uuid::Uuid;
+
+use crate::{
+    db::repos::ProviderSettingsRepo,
+    models::{CircuitBreakerSettings, RetrySettings, UpdateProviderSettings},
+};
+
+pub async fn upsert_replaces_settings(repo: &dyn ProviderSettingsRepo) {
+    let admin = Uuid::new_v4();
+    let created = repo
+        .upsert(
+            "openai",
+            UpdateProviderSettings {
+                enabled: Some(false),
+                circuit_breaker: Some(CircuitBreakerSettings {
+                    failure_threshold: Some(2),
+                    ..Default::default()
+                }),
+                retry: Some(RetrySettings {
+                    max_retries: Some(1),
+                    ..Default::default()
+                }),
+                max_concurrent: Some(8),
+            },
+            Some(admin),
+        )
+        .await
+        .expect("create settings");
+    assert_eq!(created.provider_name, "openai");
+    assert_eq!(created.enabled, Some(false));
+    assert_eq!(
+        created.circuit_breaker.as_ref().unwrap().failure_threshold,
+        Some(2)
+    );
+    assert_eq!(created.retry.as_ref().unwrap().max_retries, Some(1));
+    assert_eq!(created.max_concurrent, Some(8));
+    assert_eq!(created.updated_by, Some(admin));
+
+    let fetched = repo
+        .get("openai")
+        .await
+        .expect("get settings")
+        .expect("settings exist");
+    assert_eq!(fetched, created);
+
+    // A second upsert replaces every field but keeps the row
+    let updated = repo
+        .upsert(
+            "openai",
+            UpdateProviderSettings {
+                enabled: Some(true),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .expect("update settings");
+    assert_eq!(updated.id, created.id);
+    assert_eq!(updated.created_at, created.created_at);
+    assert_eq!(updated.enabled, Some(true));
+    assert!(updated.circuit_breaker.is_none());
+    assert!(updated.retry.is_none());
+    assert!(updated.max_concurrent.is_none());
+    assert!(updated.updated_by.is_none());
+}
+
+pub async fn list_orders_by_provider(repo: &dyn ProviderSettingsRepo) {
+    for name in ["openai", "anthropic", "bedrock"] {
+        repo.upsert(
+            name,
+            UpdateProviderSettings {
+                enabled: Some(false),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .expect("upsert settings");
+    }
+
+    let names: Vec<_> = repo
+        .list()
+        .await
+        .expect("list settings")
+        .into_iter()
+        .map(|s| s.provider_name)
+        .collect();
+    assert_eq!(names, ["anthropic", "bedrock", "openai"]);
+}
+
+pub async fn delete_removes_settings(repo: &dyn ProviderSettingsRepo) {
+    repo.upsert("openai", UpdateProviderSettings::default(), None)
+        .await
+        .expect("upsert settings");
+
+    assert!(repo.delete("openai").await.expect("delete settings"));
+    assert!(repo.get("openai").await.expect("get settings").is_none());
+    assert!(!repo.delete("openai").await.expect("delete again"));
+}
+
+#[cfg(all(test, feature = "database-sqlite"))]
+mod sqlite_tests {
+    use crate::db::{
+        sqlite::SqliteProviderSettingsRepo,
+        tests::harness::{create_sqlite_pool, run_sqlite_migrations},
+    };
+
+    async fn create_repo() -> SqliteProviderSettingsRepo {
+        let pool = create_sqlite_pool().await;
+        run_sqlite_migrations(&pool).await;
+        SqliteProviderSettingsRepo::new(pool)
+    }
+
+    macro_rules! sqlite_test {
+        ($name:ident) => {
+            #[tokio::test]
+            async fn $name() {
+                let repo = create_repo().await;
+                super::$name(&repo).await;
+            }
+        };
+    }
+
+    sqlite_test!(upsert_replaces_settings);
+    sqlite_test!(list_orders_by_provider);
+    sqlite_test!(delete_removes_settings);
+}
+
+#[cfg(all(test, feature = "database-postgres"))]
+mod postgres_tests {
+    use crate::db::{
+        postgres::PostgresProviderSettingsRepo,
+        tests::harness::postgres::{create_isolated_postgres_pool, run_postgres_migrations},
+    };
+
+    async fn create_repo() -> PostgresProviderSettingsRepo {
+        let pool = create_isolated_postgres_pool().await;
+        run_postgres_migrations(&pool).await;
+        PostgresProviderSettingsRepo::new(pool, None)
+    }
+
+    macro_rules! postgres_test {
+        ($name:ident) => {
+            #[tokio::test]
+            #[ignore = "Requires Docker - run with `cargo test -- --ignored`"]
+            async fn $name() {
+                let repo = create_repo().await;
+                super::$name(&repo).await;
+            }
+        };
+    }
+
+    postgres_test!(upsert_replaces_settings);
+    postgres_test!(list_orders_by_provider);
+    postgres_test!(delete_removes_settings);
+}
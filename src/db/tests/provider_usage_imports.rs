@@ -0,0 +1,194 @@
+//! Shared tests for ProviderUsageImportRepo implementations
+
+use chrono::NaiveDate;
+
+use crate::{
+    db::repos::{DateRange, ProviderUsageImportRepo},
+    models::{CreateProviderUsageImport, ImportedUsage, UsageImportMethod, UsageImportSource},
+};
+
+fn day(d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 3, d).unwrap()
+}
+
+fn usage(date: NaiveDate, model: &str, input_tokens: i64) -> ImportedUsage {
+    ImportedUsage {
+        date,
+        model: model.to_string(),
+        request_count: Some(input_tokens / 100),
+        input_tokens,
+        output_tokens: input_tokens / 2,
+        cost_microcents: None,
+    }
+}
+
+fn import(provider: &str, start: NaiveDate, end: NaiveDate) -> CreateProviderUsageImport {
+    CreateProviderUsageImport {
+        provider: provider.to_string(),
+        source: UsageImportSource::OpenAi,
+        method: UsageImportMethod::Api,
+        start_date: start,
+        end_date: end,
+    }
+}
+
+fn march() -> DateRange {
+    DateRange {
+        start: day(1),
+        end: day(31),
+    }
+}
+
+pub async fn record_import_replaces_overlapping_days(repo: &dyn ProviderUsageImportRepo) {
+    let first = repo
+        .record_import(
+            import("openai", day(1), day(3)),
+            &[
+                usage(day(1), "gpt-4o", 1000),
+                usage(day(2), "gpt-4o", 2000),
+                usage(day(3), "gpt-4o", 3000),
+            ],
+        )
+        .await
+        .expect("first import");
+    assert_eq!(first.row_count, 3);
+
+    // Re-import days 2-3; day 2 now has no usage at all
+    let second = repo
+        .record_import(
+            import("openai", day(2), day(3)),
+            &[usage(day(3), "gpt-4o", 3500)],
+        )
+        .await
+        .expect("second import");
+
+    let rows = repo.list_usage(march(), None).await.unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].usage.date, day(1));
+    assert_eq!(rows[0].import_id, first.id);
+    assert_eq!(rows[1].usage.date, day(3));
+    assert_eq!(rows[1].usage.input_tokens, 3500);
+    assert_eq!(rows[1].import_id, second.id);
+}
+
+pub async fn list_imports_filters_by_provider(repo: &dyn ProviderUsageImportRepo) {
+    repo.record_import(import("openai", day(1), day(1)), &[])
+        .await
+        .unwrap();
+    let mut azure = import("azure-east", day(1), day(2));
+    azure.source = UsageImportSource::Azure;
+    azure.method = UsageImportMethod::Csv;
+    repo.record_import(azure, &[usage(day(1), "gpt-4o", 10)])
+        .await
+        .unwrap();
+
+    let all = repo.list_imports(None).await.unwrap();
+    assert_eq!(all.len(), 2);
+
+    let imports = repo.list_imports(Some("azure-east")).await.unwrap();
+    assert_eq!(imports.len(), 1);
+    assert_eq!(imports[0].source, UsageImportSource::Azure);
+    assert_eq!(imports[0].method, UsageImportMethod::Csv);
+    assert_eq!(imports[0].end_date, day(2));
+}
+
+pub async fn list_usage_filters_by_provider_and_range(repo: &dyn ProviderUsageImportRepo) {
+    repo.record_import(
+        import("openai", day(1), day(10)),
+        &[usage(day(1), "gpt-4o", 100), usage(day(10), "gpt-4o", 100)],
+    )
+    .await
+    .unwrap();
+    repo.record_import(
+        import("anthropic", day(1), day(1)),
+        &[usage(day(1), "claude-sonnet-4", 100)],
+    )
+    .await
+    .unwrap();
+
+    let range = DateRange {
+        start: day(1),
+        end: day(5),
+    };
+    let rows = repo.list_usage(range.clone(), None).await.unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].provider, "anthropic");
+
+    let rows = repo.list_usage(range, Some("openai")).await.unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].usage.request_count, Some(1));
+}
+
+pub async fn delete_import_removes_usage(repo: &dyn ProviderUsageImportRepo) {
+    let created = repo
+        .record_import(
+            import("openai", day(1), day(1)),
+            &[usage(day(1), "gpt-4o", 100)],
+        )
+        .await
+        .unwrap();
+
+    assert!(repo.delete_import(created.id).await.unwrap());
+    assert!(!repo.delete_import(created.id).await.unwrap());
+    assert!(repo.list_imports(None).await.unwrap().is_empty());
+    assert!(repo.list_usage(march(), None).await.unwrap().is_empty());
+}
+
+#[cfg(all(test, feature = "database-sqlite"))]
+mod sqlite_tests {
+    use crate::db::{
+        sqlite::SqliteProviderUsageImportRepo,
+        tests::harness::{create_sqlite_pool, run_sqlite_migrations},
+    };
+
+    async fn create_repo() -> SqliteProviderUsageImportRepo {
+        let pool = create_sqlite_pool().await;
+        run_sqlite_migrations(&pool).await;
+        SqliteProviderUsageImportRepo::new(pool)
+    }
+
+    macro_rules! sqlite_test {
+        ($name:ident) => {
+            #[tokio::test]
+            async fn $name() {
+                let repo = create_repo().await;
+                super::$name(&repo).await;
+            }
+        };
+    }
+
+    sqlite_test!(record_import_replaces_overlapping_days);
+    sqlite_test!(list_imports_filters_by_provider);
+    sqlite_test!(list_usage_filters_by_provider_and_range);
+    sqlite_test!(delete_import_removes_usage);
+}
+
+#[cfg(all(test, feature = "database-postgres"))]
+mod postgres_tests {
+    use crate::db::{
+        postgres::PostgresProviderUsageImportRepo,
+        tests::harness::postgres::{create_isolated_postgres_pool, run_postgres_migrations},
+    };
+
+    async fn create_repo() -> PostgresProviderUsageImportRepo {
+        let pool = create_isolated_postgres_pool().await;
+        run_postgres_migrations(&pool).await;
+        PostgresProviderUsageImportRepo::new(pool, None)
+    }
+
+    macro_rules! postgres_test {
+        ($name:ident) => {
+            #[tokio::test]
+            #[ignore = "Requires Docker - run with `cargo test -- --ignored`"]
+            async fn $name() {
+                let repo = create_repo().await;
+                super::$name(&repo).await;
+            }
+        };
+    }
+
+    postgres_test!(record_import_replaces_overlapping_days);
+    postgres_test!(list_imports_filters_by_provider);
+    postgres_test!(list_usage_filters_by_provider_and_range);
+    postgres_test!(delete_import_removes_usage);
+}
@@ -0,0 +1,59 @@
+//! Read replica routing tests. PostgreSQL only (testcontainers, `--ignored`).
+
+#[cfg(all(test, feature = "database-postgres"))]
+mod postgres_tests {
+    use std::time::Duration;
+
+    use crate::db::{
+        DbPool, DbPoolRef,
+        tests::harness::postgres::{create_isolated_postgres_pool, run_postgres_migrations},
+    };
+
+    fn replica_healthy(db: &DbPool) -> bool {
+        match db.pool() {
+            DbPoolRef::Postgres(pools) => pools.replica_healthy(),
+            #[allow(unreachable_patterns)]
+            _ => unreachable!("Postgres pool expected"),
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "Requires Docker - run with `cargo test -- --ignored`"]
+    async fn unreachable_replica_falls_back_to_primary() {
+        let primary = create_isolated_postgres_pool().await;
+        run_postgres_migrations(&primary).await;
+        // Nothing listens on port 1, so every replica connection fails
+        let replica = sqlx::postgres::PgPoolOptions::new()
+            .acquire_timeout(Duration::from_secs(1))
+            .connect_lazy("postgres://hadrian@127.0.0.1:1/hadrian")
+            .expect("parse replica URL");
+
+        let db = DbPool::from_postgres(primary, Some(replica));
+        assert!(replica_healthy(&db));
+        assert_eq!(db.check_replica(30).await, Some(false));
+        assert!(!replica_healthy(&db));
+
+        // Reads are served by the primary
+        let orgs = db
+            .organizations()
+            .list(Default::default())
+            .await
+            .expect("list organizations from the primary");
+        assert!(orgs.items.is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore = "Requires Docker - run with `cargo test -- --ignored`"]
+    async fn caught_up_replica_serves_reads() {
+        // A server that isn't replaying WAL reports no lag, so the primary
+        // stands in for a caught-up replica
+        let primary = create_isolated_postgres_pool().await;
+        let db = DbPool::from_postgres(primary.clone(), Some(primary));
+        assert_eq!(db.check_replica(30).await, Some(true));
+        assert!(replica_healthy(&db));
+
+        let db = DbPool::from_postgres(create_isolated_postgres_pool().await, None);
+        assert_eq!(db.check_replica(30).await, None);
+        assert!(!replica_healthy(&db));
+    }
+}
@@ -0,0 +1,267 @@
+//! Shared tests for RoleElevationRepo implementations
+
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        error::DbError,
+        repos::{ListParams, RoleElevationRepo},
+    },
+    models::{ElevationStatus, NewRoleElevation},
+};
+
+fn request(org_id: Uuid, user_id: Uuid) -> NewRoleElevation {
+    NewRoleElevation {
+        org_id,
+        user_id,
+        role: "admin".to_string(),
+        justification: "Incident 42: rotate leaked provider key".to_string(),
+        duration_hours: 2,
+        requested_by: Some(user_id),
+    }
+}
+
+pub async fn approve_and_expire(repo: &dyn RoleElevationRepo, org_id: Uuid, user_id: Uuid) {
+    let created = repo
+        .create(request(org_id, user_id))
+        .await
+        .expect("create elevation");
+    assert_eq!(created.status, ElevationStatus::Pending);
+    assert!(created.expires_at.is_none());
+
+    let expires_at = Utc::now() + Duration::hours(2);
+    let approved = repo
+        .approve(created.id, None, Some("ok"), "member", expires_at)
+        .await
+        .expect("approve");
+    assert_eq!(approved.status, ElevationStatus::Active);
+    assert_eq!(approved.previous_role.as_deref(), Some("member"));
+    assert_eq!(approved.decision_note.as_deref(), Some("ok"));
+    assert!(approved.decided_at.is_some());
+
+    // Already decided
+    let again = repo
+        .approve(created.id, None, None, "member", expires_at)
+        .await;
+    assert!(matches!(again, Err(DbError::NotFound)));
+
+    assert!(repo.list_expired(Utc::now(), 10).await.unwrap().is_empty());
+    let due = repo
+        .list_expired(expires_at + Duration::seconds(1), 10)
+        .await
+        .expect("list expired");
+    assert_eq!(due.len(), 1);
+    assert_eq!(due[0].id, created.id);
+
+    assert!(
+        repo.end(
+            created.id,
+            ElevationStatus::Active,
+            ElevationStatus::Expired,
+            None
+        )
+        .await
+        .expect("expire")
+    );
+    // A second replica loses the race
+    assert!(
+        !repo
+            .end(
+                created.id,
+                ElevationStatus::Active,
+                ElevationStatus::Expired,
+                None
+            )
+            .await
+            .unwrap()
+    );
+
+    let ended = repo.get_by_id(created.id).await.unwrap().expect("exists");
+    assert_eq!(ended.status, ElevationStatus::Expired);
+    assert!(ended.ended_at.is_some());
+    assert!(
+        repo.list_expired(expires_at + Duration::seconds(1), 10)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+pub async fn one_open_request_per_member(
+    repo: &dyn RoleElevationRepo,
+    org_id: Uuid,
+    user_id: Uuid,
+) {
+    let first = repo.create(request(org_id, user_id)).await.unwrap();
+    let duplicate = repo.create(request(org_id, user_id)).await;
+    assert!(matches!(duplicate, Err(DbError::Conflict(_))));
+
+    let denied = repo
+        .deny(first.id, None, Some("not needed"))
+        .await
+        .expect("deny");
+    assert_eq!(denied.status, ElevationStatus::Denied);
+    assert!(matches!(
+        repo.deny(first.id, None, None).await,
+        Err(DbError::NotFound)
+    ));
+
+    // A closed request no longer blocks a new one
+    let second = repo
+        .create(request(org_id, user_id))
+        .await
+        .expect("create after deny");
+    assert!(
+        repo.end(
+            second.id,
+            ElevationStatus::Pending,
+            ElevationStatus::Revoked,
+            Some(user_id)
+        )
+        .await
+        .unwrap()
+    );
+    let revoked = repo.get_by_id(second.id).await.unwrap().unwrap();
+    assert_eq!(revoked.status, ElevationStatus::Revoked);
+    assert_eq!(revoked.ended_by, Some(user_id));
+}
+
+pub async fn list_by_org_paginates(repo: &dyn RoleElevationRepo, org_id: Uuid, user_id: Uuid) {
+    for _ in 0..3 {
+        let e = repo.create(request(org_id, user_id)).await.unwrap();
+        repo.deny(e.id, None, None).await.unwrap();
+    }
+
+    let page = repo
+        .list_by_org(
+            org_id,
+            ListParams {
+                limit: Some(2),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("list first page");
+    assert_eq!(page.items.len(), 2);
+    assert!(page.has_more);
+
+    let next = repo
+        .list_by_org(
+            org_id,
+            ListParams {
+                limit: Some(2),
+                cursor: page.cursors.next.clone(),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("list second page");
+    assert_eq!(next.items.len(), 1);
+    assert!(!next.has_more);
+
+    let other = repo
+        .list_by_org(Uuid::new_v4(), ListParams::default())
+        .await
+        .unwrap();
+    assert!(other.items.is_empty());
+}
+
+#[cfg(all(test, feature = "database-sqlite"))]
+mod sqlite_tests {
+    use uuid::Uuid;
+
+    use crate::{
+        db::{
+            repos::{OrganizationRepo, UserRepo},
+            sqlite::{SqliteOrganizationRepo, SqliteRoleElevationRepo, SqliteUserRepo},
+            tests::harness::{create_sqlite_pool, run_sqlite_migrations},
+        },
+        models::{CreateOrganization, CreateUser},
+    };
+
+    async fn create_repo() -> (SqliteRoleElevationRepo, Uuid, Uuid) {
+        let pool = create_sqlite_pool().await;
+        run_sqlite_migrations(&pool).await;
+        let org = SqliteOrganizationRepo::new(pool.clone())
+            .create(CreateOrganization {
+                slug: "acme".to_string(),
+                name: "Acme".to_string(),
+            })
+            .await
+            .expect("create org");
+        let user = SqliteUserRepo::new(pool.clone())
+            .create(CreateUser {
+                external_id: "alice".to_string(),
+                email: None,
+                name: None,
+            })
+            .await
+            .expect("create user");
+        (SqliteRoleElevationRepo::new(pool), org.id, user.id)
+    }
+
+    macro_rules! sqlite_test {
+        ($name:ident) => {
+            #[tokio::test]
+            async fn $name() {
+                let (repo, org_id, user_id) = create_repo().await;
+                super::$name(&repo, org_id, user_id).await;
+            }
+        };
+    }
+
+    sqlite_test!(approve_and_expire);
+    sqlite_test!(one_open_request_per_member);
+    sqlite_test!(list_by_org_paginates);
+}
+
+#[cfg(all(test, feature = "database-postgres"))]
+mod postgres_tests {
+    use uuid::Uuid;
+
+    use crate::{
+        db::{
+            postgres::{PostgresOrganizationRepo, PostgresRoleElevationRepo, PostgresUserRepo},
+            repos::{OrganizationRepo, UserRepo},
+            tests::harness::postgres::{create_isolated_postgres_pool, run_postgres_migrations},
+        },
+        models::{CreateOrganization, CreateUser},
+    };
+
+    async fn create_repo() -> (PostgresRoleElevationRepo, Uuid, Uuid) {
+        let pool = create_isolated_postgres_pool().await;
+        run_postgres_migrations(&pool).await;
+        let org = PostgresOrganizationRepo::new(pool.clone(), None)
+            .create(CreateOrganization {
+                slug: "acme".to_string(),
+                name: "Acme".to_string(),
+            })
+            .await
+            .expect("create org");
+        let user = PostgresUserRepo::new(pool.clone(), None)
+            .create(CreateUser {
+                external_id: "alice".to_string(),
+                email: None,
+                name: None,
+            })
+            .await
+            .expect("create user");
+        (PostgresRoleElevationRepo::new(pool, None), org.id, user.id)
+    }
+
+    macro_rules! postgres_test {
+        ($name:ident) => {
+            #[tokio::test]
+            #[ignore = "Requires Docker - run with `cargo test -- --ignored`"]
+            async fn $name() {
+                let (repo, org_id, user_id) = create_repo().await;
+                super::$name(&repo, org_id, user_id).await;
+            }
+        };
+    }
+
+    postgres_test!(approve_and_expire);
+    postgres_test!(one_open_request_per_member);
+    postgres_test!(list_by_org_paginates);
+}
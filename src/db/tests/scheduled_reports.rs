@@ -0,0 +1,254 @@
+//! Shared tests for ReportRunRepo implementations
+
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use crate::{
+    config::{ReportFormat, ReportKind},
+    db::repos::{ListParams, ReportRunRepo},
+    models::{CreateReportRun, ReportRunFilter, ReportRunStatus},
+};
+
+fn run_input(report_name: &str, org_id: Uuid, period_start: NaiveDate) -> CreateReportRun {
+    CreateReportRun {
+        report_name: report_name.to_string(),
+        kind: ReportKind::UsageSummary,
+        org_id,
+        period_start,
+        period_end: period_start + chrono::Duration::days(6),
+        format: ReportFormat::Json,
+        delivery: "webhook".to_string(),
+        status: ReportRunStatus::Delivered,
+        error: None,
+        content: Some("{\"total_cost_microcents\":0}".to_string()),
+    }
+}
+
+fn monday() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 3, 3).unwrap()
+}
+
+pub async fn create_and_get_round_trips(repo: &dyn ReportRunRepo, org_id: Uuid) {
+    let created = repo
+        .create(run_input("weekly", org_id, monday()))
+        .await
+        .expect("create run");
+
+    let fetched = repo
+        .get_by_id(created.id)
+        .await
+        .expect("get run")
+        .expect("run exists");
+    assert_eq!(fetched.report_name, "weekly");
+    assert_eq!(fetched.kind, ReportKind::UsageSummary);
+    assert_eq!(fetched.format, ReportFormat::Json);
+    assert_eq!(fetched.status, ReportRunStatus::Delivered);
+    assert_eq!(fetched.period_start, monday());
+    assert_eq!(
+        fetched.period_end,
+        NaiveDate::from_ymd_opt(2025, 3, 9).unwrap()
+    );
+    assert_eq!(fetched.created_at, created.created_at);
+    assert_eq!(
+        fetched.content.as_deref(),
+        Some("{\"total_cost_microcents\":0}"),
+        "get_by_id loads the rendered content"
+    );
+
+    assert!(repo.get_by_id(Uuid::new_v4()).await.unwrap().is_none());
+}
+
+pub async fn list_for_period_returns_attempts_in_order(repo: &dyn ReportRunRepo, org_id: Uuid) {
+    let mut failed = run_input("weekly", org_id, monday());
+    failed.status = ReportRunStatus::Failed;
+    failed.error = Some("connection refused".to_string());
+    failed.content = None;
+    repo.create(failed).await.expect("create failed run");
+    repo.create(run_input("weekly", org_id, monday()))
+        .await
+        .expect("create delivered run");
+
+    // Different period and different report must not be returned
+    let next_week = monday() + chrono::Duration::days(7);
+    repo.create(run_input("weekly", org_id, next_week))
+        .await
+        .expect("create next week");
+    repo.create(run_input("other", org_id, monday()))
+        .await
+        .expect("create other report");
+
+    let runs = repo
+        .list_for_period("weekly", org_id, monday())
+        .await
+        .expect("list for period");
+    assert_eq!(runs.len(), 2);
+    assert_eq!(runs[0].status, ReportRunStatus::Failed);
+    assert_eq!(runs[0].error.as_deref(), Some("connection refused"));
+    assert_eq!(runs[1].status, ReportRunStatus::Delivered);
+    assert!(
+        runs.iter().all(|r| r.content.is_none()),
+        "period lookups do not load content"
+    );
+}
+
+pub async fn list_filters_and_paginates(repo: &dyn ReportRunRepo, org_id: Uuid) {
+    for i in 0..3 {
+        repo.create(run_input(
+            "weekly",
+            org_id,
+            monday() + chrono::Duration::days(7 * i),
+        ))
+        .await
+        .expect("create run");
+    }
+    repo.create(run_input("keys", org_id, monday()))
+        .await
+        .expect("create other report");
+
+    let all = repo
+        .list(
+            ReportRunFilter {
+                org_id: Some(org_id),
+                report_name: None,
+            },
+            ListParams::default(),
+        )
+        .await
+        .expect("list all");
+    assert_eq!(all.items.len(), 4);
+    assert!(!all.has_more);
+
+    let first = repo
+        .list(
+            ReportRunFilter {
+                org_id: None,
+                report_name: Some("weekly".to_string()),
+            },
+            ListParams {
+                limit: Some(2),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("list first page");
+    assert_eq!(first.items.len(), 2);
+    assert!(first.has_more);
+    assert!(first.items.iter().all(|r| r.report_name == "weekly"));
+    assert!(first.items[0].created_at >= first.items[1].created_at);
+
+    let second = repo
+        .list(
+            ReportRunFilter {
+                org_id: None,
+                report_name: Some("weekly".to_string()),
+            },
+            ListParams {
+                limit: Some(2),
+                cursor: first.cursors.next.clone(),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("list second page");
+    assert_eq!(second.items.len(), 1);
+    assert!(!second.has_more);
+    assert!(
+        first.items.iter().all(|r| r.id != second.items[0].id),
+        "pages do not overlap"
+    );
+
+    let other_org = repo
+        .list(
+            ReportRunFilter {
+                org_id: Some(Uuid::new_v4()),
+                report_name: None,
+            },
+            ListParams::default(),
+        )
+        .await
+        .expect("list other org");
+    assert!(other_org.items.is_empty());
+}
+
+#[cfg(all(test, feature = "database-sqlite"))]
+mod sqlite_tests {
+    use uuid::Uuid;
+
+    use crate::{
+        db::{
+            repos::OrganizationRepo,
+            sqlite::{SqliteOrganizationRepo, SqliteReportRunRepo},
+            tests::harness::{create_sqlite_pool, run_sqlite_migrations},
+        },
+        models::CreateOrganization,
+    };
+
+    async fn create_repo() -> (SqliteReportRunRepo, Uuid) {
+        let pool = create_sqlite_pool().await;
+        run_sqlite_migrations(&pool).await;
+        let org = SqliteOrganizationRepo::new(pool.clone())
+            .create(CreateOrganization {
+                slug: "acme".to_string(),
+                name: "Acme".to_string(),
+            })
+            .await
+            .expect("create org");
+        (SqliteReportRunRepo::new(pool), org.id)
+    }
+
+    macro_rules! sqlite_test {
+        ($name:ident) => {
+            #[tokio::test]
+            async fn $name() {
+                let (repo, org_id) = create_repo().await;
+                super::$name(&repo, org_id).await;
+            }
+        };
+    }
+
+    sqlite_test!(create_and_get_round_trips);
+    sqlite_test!(list_for_period_returns_attempts_in_order);
+    sqlite_test!(list_filters_and_paginates);
+}
+
+#[cfg(all(test, feature = "database-postgres"))]
+mod postgres_tests {
+    use uuid::Uuid;
+
+    use crate::{
+        db::{
+            postgres::{PostgresOrganizationRepo, PostgresReportRunRepo},
+            repos::OrganizationRepo,
+            tests::harness::postgres::{create_isolated_postgres_pool, run_postgres_migrations},
+        },
+        models::CreateOrganization,
+    };
+
+    async fn create_repo() -> (PostgresReportRunRepo, Uuid) {
+        let pool = create_isolated_postgres_pool().await;
+        run_postgres_migrations(&pool).await;
+        let org = PostgresOrganizationRepo::new(pool.clone(), None)
+            .create(CreateOrganization {
+                slug: "acme".to_string(),
+                name: "Acme".to_string(),
+            })
+            .await
+            .expect("create org");
+        (PostgresReportRunRepo::new(pool, None), org.id)
+    }
+
+    macro_rules! postgres_test {
+        ($name:ident) => {
+            #[tokio::test]
+            #[ignore = "Requires Docker - run with `cargo test -- --ignored`"]
+            async fn $name() {
+                let (repo, org_id) = create_repo().await;
+                super::$name(&repo, org_id).await;
+            }
+        };
+    }
+
+    postgres_test!(create_and_get_round_trips);
+    postgres_test!(list_for_period_returns_attempts_in_order);
+    postgres_test!(list_filters_and_paginates);
+}
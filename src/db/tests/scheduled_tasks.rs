@@ -0,0 +1,371 @@
+//! Shared tests for ScheduledTaskRepo implementations
+
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::{
+    db::{error::DbError, repos::ScheduledTaskRepo},
+    models::{
+        CreateScheduledTask, CreateScheduledTaskRun, ScheduledTaskDelivery, ScheduledTaskProgress,
+        ScheduledTaskRunStatus, UpdateScheduledTask,
+    },
+};
+
+fn create_task_input(name: &str) -> CreateScheduledTask {
+    CreateScheduledTask {
+        name: name.to_string(),
+        description: Some("Daily summary of open incidents".to_string()),
+        schedule: "0 8 * * *".to_string(),
+        model: "openai/gpt-4o-mini".to_string(),
+        instructions: Some("Be brief.".to_string()),
+        prompt: "Summarize yesterday's incidents.".to_string(),
+        delivery: ScheduledTaskDelivery::Webhook {
+            url: "https://hooks.example.com/summary".to_string(),
+            signing_secret: Some("whsec_test".to_string()),
+        },
+        enabled: true,
+    }
+}
+
+fn run_input(
+    task_id: Uuid,
+    status: ScheduledTaskRunStatus,
+    minutes_ago: i64,
+) -> CreateScheduledTaskRun {
+    let started_at = Utc::now() - Duration::minutes(minutes_ago);
+    CreateScheduledTaskRun {
+        id: Uuid::new_v4(),
+        task_id,
+        status,
+        output_text: Some("All quiet.".to_string()),
+        error: None,
+        input_tokens: 120,
+        output_tokens: 30,
+        cost_microcents: Some(900),
+        started_at,
+        finished_at: started_at + Duration::seconds(2),
+    }
+}
+
+pub async fn task_crud_round_trips(repo: &dyn ScheduledTaskRepo) {
+    let org_id = Uuid::new_v4();
+    let next_run_at = Utc::now() + Duration::hours(1);
+    let created = repo
+        .create(org_id, create_task_input("daily"), Some(next_run_at))
+        .await
+        .expect("create task");
+    assert_eq!(created.org_id, org_id);
+    assert_eq!(created.consecutive_failures, 0);
+
+    let fetched = repo
+        .get(created.id)
+        .await
+        .expect("get task")
+        .expect("task exists");
+    assert_eq!(fetched, created);
+    // The signing secret is stored, but never serialized
+    let json = serde_json::to_value(&fetched).unwrap();
+    assert_eq!(json["delivery"]["type"], "webhook");
+    assert!(json["delivery"].get("signing_secret").is_none());
+
+    repo.create(org_id, create_task_input("weekly"), None)
+        .await
+        .expect("create second task");
+    repo.create(Uuid::new_v4(), create_task_input("daily"), None)
+        .await
+        .expect("same name in another org");
+    let names: Vec<_> = repo
+        .list_by_org(org_id)
+        .await
+        .expect("list tasks")
+        .into_iter()
+        .map(|t| t.name)
+        .collect();
+    assert_eq!(names, vec!["daily", "weekly"]);
+    assert_eq!(repo.count_by_org(org_id).await.expect("count tasks"), 2);
+
+    let conversation_id = Uuid::new_v4();
+    let updated = repo
+        .update(
+            created.id,
+            UpdateScheduledTask {
+                prompt: Some("Summarize this week's incidents.".to_string()),
+                delivery: Some(ScheduledTaskDelivery::Conversation { conversation_id }),
+                enabled: Some(false),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .expect("update task");
+    assert_eq!(updated.prompt, "Summarize this week's incidents.");
+    assert_eq!(
+        updated.delivery,
+        ScheduledTaskDelivery::Conversation { conversation_id }
+    );
+    assert!(!updated.enabled);
+    assert!(updated.next_run_at.is_none());
+    assert_eq!(updated.name, "daily");
+    assert_eq!(
+        repo.get(created.id).await.expect("get updated").as_ref(),
+        Some(&updated)
+    );
+
+    let err = repo
+        .update(
+            created.id,
+            UpdateScheduledTask {
+                name: Some("weekly".to_string()),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .expect_err("rename to an existing name should fail");
+    assert!(matches!(err, DbError::Conflict(_)));
+
+    repo.delete(created.id).await.expect("delete task");
+    assert!(repo.get(created.id).await.expect("get deleted").is_none());
+    let err = repo
+        .delete(created.id)
+        .await
+        .expect_err("delete missing task");
+    assert!(matches!(err, DbError::NotFound));
+}
+
+pub async fn duplicate_names_conflict(repo: &dyn ScheduledTaskRepo) {
+    let org_id = Uuid::new_v4();
+    repo.create(org_id, create_task_input("daily"), None)
+        .await
+        .expect("create task");
+    let err = repo
+        .create(org_id, create_task_input("daily"), None)
+        .await
+        .expect_err("duplicate name should fail");
+    assert!(matches!(err, DbError::Conflict(_)));
+}
+
+pub async fn only_enabled_due_tasks_are_listed(repo: &dyn ScheduledTaskRepo) {
+    let org_id = Uuid::new_v4();
+    let now = Utc::now();
+    let overdue = repo
+        .create(
+            org_id,
+            create_task_input("overdue"),
+            Some(now - Duration::hours(2)),
+        )
+        .await
+        .expect("create overdue task");
+    let due = repo
+        .create(
+            org_id,
+            create_task_input("due"),
+            Some(now - Duration::minutes(1)),
+        )
+        .await
+        .expect("create due task");
+    repo.create(
+        org_id,
+        create_task_input("later"),
+        Some(now + Duration::hours(1)),
+    )
+    .await
+    .expect("create later task");
+    let mut disabled = create_task_input("disabled");
+    disabled.enabled = false;
+    repo.create(org_id, disabled, Some(now - Duration::hours(1)))
+        .await
+        .expect("create disabled task");
+
+    let ids: Vec<_> = repo
+        .list_due(now, 10)
+        .await
+        .expect("list due")
+        .into_iter()
+        .map(|t| t.id)
+        .collect();
+    assert_eq!(ids, vec![overdue.id, due.id]);
+    assert_eq!(repo.list_due(now, 1).await.expect("list limited").len(), 1);
+}
+
+pub async fn record_run_updates_task(repo: &dyn ScheduledTaskRepo) {
+    let org_id = Uuid::new_v4();
+    let task = repo
+        .create(
+            org_id,
+            create_task_input("daily"),
+            Some(Utc::now() - Duration::minutes(1)),
+        )
+        .await
+        .expect("create task");
+
+    let next_run_at = Utc::now() + Duration::days(1);
+    let mut failed = run_input(task.id, ScheduledTaskRunStatus::Failed, 0);
+    failed.output_text = None;
+    failed.error = Some("webhook returned HTTP 500".to_string());
+    let run = repo
+        .record_run(
+            failed,
+            ScheduledTaskProgress {
+                next_run_at: Some(next_run_at),
+                consecutive_failures: 3,
+                enabled: false,
+            },
+        )
+        .await
+        .expect("record run");
+    assert_eq!(run.status, ScheduledTaskRunStatus::Failed);
+
+    let task = repo
+        .get(task.id)
+        .await
+        .expect("get task")
+        .expect("task exists");
+    assert_eq!(task.consecutive_failures, 3);
+    assert!(!task.enabled);
+    assert_eq!(task.last_run_at, Some(run.finished_at));
+    assert!(
+        repo.list_due(Utc::now() + Duration::days(2), 10)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    // Enabling the task again resets its failure count
+    let task = repo
+        .update(
+            task.id,
+            UpdateScheduledTask {
+                enabled: Some(true),
+                ..Default::default()
+            },
+            Some(next_run_at),
+        )
+        .await
+        .expect("enable task");
+    assert_eq!(task.consecutive_failures, 0);
+
+    let runs = repo.list_runs(task.id, 10).await.expect("list runs");
+    assert_eq!(runs, vec![run]);
+
+    let err = repo
+        .record_run(
+            run_input(Uuid::new_v4(), ScheduledTaskRunStatus::Succeeded, 0),
+            ScheduledTaskProgress {
+                next_run_at: None,
+                consecutive_failures: 0,
+                enabled: true,
+            },
+        )
+        .await
+        .expect_err("missing task should fail");
+    assert!(matches!(err, DbError::NotFound));
+}
+
+pub async fn runs_are_listed_newest_first_and_pruned(repo: &dyn ScheduledTaskRepo) {
+    let task = repo
+        .create(Uuid::new_v4(), create_task_input("daily"), None)
+        .await
+        .expect("create task");
+    let progress = ScheduledTaskProgress {
+        next_run_at: None,
+        consecutive_failures: 0,
+        enabled: true,
+    };
+
+    let mut ids = Vec::new();
+    for minutes_ago in [30, 20, 10] {
+        let run = repo
+            .record_run(
+                run_input(task.id, ScheduledTaskRunStatus::Succeeded, minutes_ago),
+                progress,
+            )
+            .await
+            .expect("record run");
+        ids.push(run.id);
+    }
+
+    let listed: Vec<_> = repo
+        .list_runs(task.id, 10)
+        .await
+        .expect("list runs")
+        .into_iter()
+        .map(|r| r.id)
+        .collect();
+    assert_eq!(listed, vec![ids[2], ids[1], ids[0]]);
+
+    assert_eq!(repo.prune_runs(task.id, 2).await.expect("prune runs"), 1);
+    let listed: Vec<_> = repo
+        .list_runs(task.id, 10)
+        .await
+        .expect("list pruned runs")
+        .into_iter()
+        .map(|r| r.id)
+        .collect();
+    assert_eq!(listed, vec![ids[2], ids[1]]);
+
+    // Deleting the task takes its runs with it
+    repo.delete(task.id).await.expect("delete task");
+    assert!(repo.list_runs(task.id, 10).await.unwrap().is_empty());
+}
+
+#[cfg(all(test, feature = "database-sqlite"))]
+mod sqlite_tests {
+    use crate::db::{
+        sqlite::SqliteScheduledTaskRepo,
+        tests::harness::{create_sqlite_pool, run_sqlite_migrations},
+    };
+
+    async fn create_repo() -> SqliteScheduledTaskRepo {
+        let pool = create_sqlite_pool().await;
+        run_sqlite_migrations(&pool).await;
+        SqliteScheduledTaskRepo::new(pool)
+    }
+
+    macro_rules! sqlite_test {
+        ($name:ident) => {
+            #[tokio::test]
+            async fn $name() {
+                let repo = create_repo().await;
+                super::$name(&repo).await;
+            }
+        };
+    }
+
+    sqlite_test!(task_crud_round_trips);
+    sqlite_test!(duplicate_names_conflict);
+    sqlite_test!(only_enabled_due_tasks_are_listed);
+    sqlite_test!(record_run_updates_task);
+    sqlite_test!(runs_are_listed_newest_first_and_pruned);
+}
+
+#[cfg(all(test, feature = "database-postgres"))]
+mod postgres_tests {
+    use crate::db::{
+        postgres::PostgresScheduledTaskRepo,
+        tests::harness::postgres::{create_isolated_postgres_pool, run_postgres_migrations},
+    };
+
+    async fn create_repo() -> PostgresScheduledTaskRepo {
+        let pool = create_isolated_postgres_pool().await;
+        run_postgres_migrations(&pool).await;
+        PostgresScheduledTaskRepo::new(pool, None)
+    }
+
+    macro_rules! postgres_test {
+        ($name:ident) => {
+            #[tokio::test]
+            #[ignore = "Requires Docker - run with `cargo test -- --ignored`"]
+            async fn $name() {
+                let repo = create_repo().await;
+                super::$name(&repo).await;
+            }
+        };
+    }
+
+    postgres_test!(task_crud_round_trips);
+    postgres_test!(duplicate_names_conflict);
+    postgres_test!(only_enabled_due_tasks_are_listed);
+    postgres_test!(record_run_updates_task);
+    postgres_test!(runs_are_listed_newest_first_and_pruned);
+}
@@ -0,0 +1,233 @@
+//! Shared tests for ShadowResultRepo implementations
+
+use uuid::Uuid;
+
+use crate::{
+    db::repos::{ListParams, ShadowResultRepo},
+    models::{CreateShadowResult, ShadowResultFilter},
+};
+
+fn result_input(rule: &str, similarity: Option<f64>) -> CreateShadowResult {
+    CreateShadowResult {
+        rule: rule.to_string(),
+        primary_provider: "openai".to_string(),
+        primary_model: "gpt-4o".to_string(),
+        primary_status: 200,
+        primary_latency_ms: 800,
+        primary_output_tokens: Some(10),
+        primary_cost_microcents: Some(1000),
+        primary_output: "Hello".to_string(),
+        shadow_provider: "azure".to_string(),
+        shadow_model: "gpt-4o".to_string(),
+        shadow_status: similarity.map(|_| 200),
+        shadow_latency_ms: Some(600),
+        shadow_output_tokens: similarity.map(|_| 12),
+        shadow_cost_microcents: similarity.map(|_| 900),
+        shadow_output: if similarity == Some(1.0) {
+            "Hello".to_string()
+        } else {
+            String::new()
+        },
+        shadow_error: similarity.is_none().then(|| "timed out".to_string()),
+        similarity,
+        ..Default::default()
+    }
+}
+
+pub async fn create_get_and_list(repo: &dyn ShadowResultRepo) {
+    let org_id = Uuid::new_v4();
+    let created = repo
+        .create(CreateShadowResult {
+            org_id: Some(org_id),
+            ..result_input("azure-migration", Some(1.0))
+        })
+        .await
+        .expect("create result");
+
+    let fetched = repo
+        .get_by_id(created.id)
+        .await
+        .expect("get result")
+        .expect("result exists");
+    assert_eq!(fetched.rule, "azure-migration");
+    assert_eq!(fetched.org_id, Some(org_id));
+    assert_eq!(fetched.project_id, None);
+    assert_eq!(fetched.primary_status, 200);
+    assert_eq!(fetched.shadow_status, Some(200));
+    assert_eq!(fetched.shadow_cost_microcents, Some(900));
+    assert_eq!(fetched.primary_input_tokens, None);
+    assert_eq!(fetched.similarity, Some(1.0));
+    assert!(repo.get_by_id(Uuid::new_v4()).await.unwrap().is_none());
+
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    repo.create(result_input("azure-migration", None))
+        .await
+        .expect("create failed result");
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    repo.create(result_input("other", Some(0.5)))
+        .await
+        .expect("create other rule");
+
+    let first = repo
+        .list(
+            &ShadowResultFilter::default(),
+            ListParams {
+                limit: Some(2),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("list first page");
+    assert_eq!(first.items.len(), 2);
+    assert!(first.has_more);
+    assert_eq!(first.items[0].rule, "other", "newest first");
+
+    let second = repo
+        .list(
+            &ShadowResultFilter::default(),
+            ListParams {
+                limit: Some(2),
+                cursor: first.cursors.next.clone(),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("list second page");
+    assert_eq!(second.items.len(), 1);
+    assert_eq!(second.items[0].id, created.id);
+
+    let by_rule = repo
+        .list(
+            &ShadowResultFilter {
+                rule: Some("azure-migration".to_string()),
+                ..Default::default()
+            },
+            ListParams::default(),
+        )
+        .await
+        .expect("list by rule");
+    assert_eq!(by_rule.items.len(), 2);
+
+    let by_org = repo
+        .list(
+            &ShadowResultFilter {
+                org_id: Some(org_id),
+                ..Default::default()
+            },
+            ListParams::default(),
+        )
+        .await
+        .expect("list by org");
+    assert_eq!(by_org.items.len(), 1);
+}
+
+pub async fn report_and_delete(repo: &dyn ShadowResultRepo) {
+    repo.create(result_input("azure-migration", Some(1.0)))
+        .await
+        .expect("create identical result");
+    repo.create(result_input("azure-migration", Some(0.5)))
+        .await
+        .expect("create differing result");
+    repo.create(result_input("azure-migration", None))
+        .await
+        .expect("create failed result");
+    repo.create(result_input("other", Some(1.0)))
+        .await
+        .expect("create other rule");
+
+    let report = repo
+        .report(&ShadowResultFilter {
+            rule: Some("azure-migration".to_string()),
+            ..Default::default()
+        })
+        .await
+        .expect("report");
+    assert_eq!(report.len(), 1);
+    let row = &report[0];
+    assert_eq!(row.samples, 3);
+    assert_eq!(row.primary_errors, 0);
+    assert_eq!(row.shadow_errors, 1);
+    assert_eq!(row.identical, 1);
+    assert!((row.avg_similarity.unwrap() - 0.75).abs() < 1e-9);
+    assert!((row.primary_avg_latency_ms.unwrap() - 800.0).abs() < 1e-9);
+    assert_eq!(row.primary_output_tokens, 30);
+    assert_eq!(row.shadow_output_tokens, 24);
+    assert_eq!(row.primary_cost_microcents, 3000);
+    assert_eq!(row.shadow_cost_microcents, 1800);
+
+    let all = repo
+        .report(&ShadowResultFilter::default())
+        .await
+        .expect("report all");
+    assert_eq!(all.len(), 2);
+
+    let deleted = repo
+        .delete(&ShadowResultFilter {
+            rule: Some("azure-migration".to_string()),
+            ..Default::default()
+        })
+        .await
+        .expect("delete by rule");
+    assert_eq!(deleted, 3);
+    let remaining = repo
+        .list(&ShadowResultFilter::default(), ListParams::default())
+        .await
+        .expect("list remaining");
+    assert_eq!(remaining.items.len(), 1);
+    assert_eq!(remaining.items[0].rule, "other");
+}
+
+#[cfg(all(test, feature = "database-sqlite"))]
+mod sqlite_tests {
+    use crate::db::{
+        sqlite::SqliteShadowResultRepo,
+        tests::harness::{create_sqlite_pool, run_sqlite_migrations},
+    };
+
+    async fn create_repo() -> SqliteShadowResultRepo {
+        let pool = create_sqlite_pool().await;
+        run_sqlite_migrations(&pool).await;
+        SqliteShadowResultRepo::new(pool)
+    }
+
+    macro_rules! sqlite_test {
+        ($name:ident) => {
+            #[tokio::test]
+            async fn $name() {
+                let repo = create_repo().await;
+                super::$name(&repo).await;
+            }
+        };
+    }
+
+    sqlite_test!(create_get_and_list);
+    sqlite_test!(report_and_delete);
+}
+
+#[cfg(all(test, feature = "database-postgres"))]
+mod postgres_tests {
+    use crate::db::{
+        postgres::PostgresShadowResultRepo,
+        tests::harness::postgres::{create_isolated_postgres_pool, run_postgres_migrations},
+    };
+
+    async fn create_repo() -> PostgresShadowResultRepo {
+        let pool = create_isolated_postgres_pool().await;
+        run_postgres_migrations(&pool).await;
+        PostgresShadowResultRepo::new(pool, None)
+    }
+
+    macro_rules! postgres_test {
+        ($name:ident) => {
+            #[tokio::test]
+            #[ignore = "Requires Docker - run with `cargo test -- --ignored`"]
+            async fn $name() {
+                let repo = create_repo().await;
+                super::$name(&repo).await;
+            }
+        };
+    }
+
+    postgres_test!(create_get_and_list);
+    postgres_test!(report_and_delete);
+}
@@ -108,6 +108,20 @@ fn create_usage_entry(
         tool_results_count: None,
         tool_runtime_seconds: None,
         tool_exit_code: None,
+        jwt_subject: None,
+        error_code: None,
+        structured_output_repairs: None,
+        context_original_tokens: None,
+        context_compressed_tokens: None,
+        degraded_from_model: None,
+        degradation_reason: None,
+        ttft_ms: None,
+        inter_token_latency_ms: None,
+        output_tokens_per_second: None,
+        idempotency_key: None,
+        provider_attempts: None,
+        suspected_duplicate_attempts: None,
+        tags: None,
     }
 }
 
@@ -152,6 +166,20 @@ fn create_usage_entry_with_referer(
         tool_results_count: None,
         tool_runtime_seconds: None,
         tool_exit_code: None,
+        jwt_subject: None,
+        error_code: None,
+        structured_output_repairs: None,
+        context_original_tokens: None,
+        context_compressed_tokens: None,
+        degraded_from_model: None,
+        degradation_reason: None,
+        ttft_ms: None,
+        inter_token_latency_ms: None,
+        output_tokens_per_second: None,
+        idempotency_key: None,
+        provider_attempts: None,
+        suspected_duplicate_attempts: None,
+        tags: None,
     }
 }
 
@@ -196,6 +224,20 @@ fn create_usage_entry_at_time(
         tool_results_count: None,
         tool_runtime_seconds: None,
         tool_exit_code: None,
+        jwt_subject: None,
+        error_code: None,
+        structured_output_repairs: None,
+        context_original_tokens: None,
+        context_compressed_tokens: None,
+        degraded_from_model: None,
+        degradation_reason: None,
+        ttft_ms: None,
+        inter_token_latency_ms: None,
+        output_tokens_per_second: None,
+        idempotency_key: None,
+        provider_attempts: None,
+        suspected_duplicate_attempts: None,
+        tags: None,
     }
 }
 
@@ -250,6 +292,20 @@ fn create_attributed_usage_entry(
         tool_results_count: None,
         tool_runtime_seconds: None,
         tool_exit_code: None,
+        jwt_subject: None,
+        error_code: None,
+        structured_output_repairs: None,
+        context_original_tokens: None,
+        context_compressed_tokens: None,
+        degraded_from_model: None,
+        degradation_reason: None,
+        ttft_ms: None,
+        inter_token_latency_ms: None,
+        output_tokens_per_second: None,
+        idempotency_key: None,
+        provider_attempts: None,
+        suspected_duplicate_attempts: None,
+        tags: None,
     }
 }
 
@@ -418,6 +474,93 @@ pub async fn test_log_tool_runtime_seconds_round_trip(ctx: &UsageTestContext<'_>
     );
 }
 
+pub async fn test_log_error_code_filter(ctx: &UsageTestContext<'_>) {
+    use crate::db::repos::UsageLogQuery;
+    let org_id = ctx.create_test_org("test-org-errcode").await;
+    let api_key_id = ctx.create_test_api_key(org_id, "test-key-errcode").await;
+
+    let ok = create_usage_entry(api_key_id, "gpt-4", "openai", 100, 50, Some(10));
+    let mut failed = create_usage_entry(api_key_id, "gpt-4", "openai", 0, 0, Some(0));
+    failed.status_code = Some(429);
+    failed.error_code = Some("rate_limited".to_string());
+
+    ctx.usage_repo
+        .log_batch(vec![ok, failed])
+        .await
+        .expect("Failed to log batch");
+
+    let listed = ctx
+        .usage_repo
+        .list_logs(UsageLogQuery {
+            api_key_id: Some(api_key_id),
+            error_code: Some("rate_limited".to_string()),
+            limit: Some(10),
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to list logs");
+
+    assert_eq!(listed.items.len(), 1);
+    assert_eq!(listed.items[0].error_code.as_deref(), Some("rate_limited"));
+    assert_eq!(listed.items[0].status_code, Some(429));
+}
+
+pub async fn test_log_structured_output_repairs(ctx: &UsageTestContext<'_>) {
+    use crate::db::repos::UsageLogQuery;
+    let org_id = ctx.create_test_org("test-org-repairs").await;
+    let api_key_id = ctx.create_test_api_key(org_id, "test-key-repairs").await;
+
+    let mut entry = create_usage_entry(api_key_id, "gpt-4", "openai", 300, 90, Some(30));
+    entry.structured_output_repairs = Some(2);
+    ctx.usage_repo
+        .log(entry)
+        .await
+        .expect("Failed to log usage");
+
+    let listed = ctx
+        .usage_repo
+        .list_logs(UsageLogQuery {
+            api_key_id: Some(api_key_id),
+            limit: Some(10),
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to list logs");
+
+    assert_eq!(listed.items.len(), 1);
+    assert_eq!(listed.items[0].structured_output_repairs, Some(2));
+}
+
+pub async fn test_log_context_compression_tokens(ctx: &UsageTestContext<'_>) {
+    use crate::db::repos::UsageLogQuery;
+    let org_id = ctx.create_test_org("test-org-compression").await;
+    let api_key_id = ctx
+        .create_test_api_key(org_id, "test-key-compression")
+        .await;
+
+    let mut entry = create_usage_entry(api_key_id, "gpt-4", "openai", 30000, 200, Some(100));
+    entry.context_original_tokens = Some(52000);
+    entry.context_compressed_tokens = Some(29800);
+    ctx.usage_repo
+        .log_batch(vec![entry])
+        .await
+        .expect("Failed to log batch");
+
+    let listed = ctx
+        .usage_repo
+        .list_logs(UsageLogQuery {
+            api_key_id: Some(api_key_id),
+            limit: Some(10),
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to list logs");
+
+    assert_eq!(listed.items.len(), 1);
+    assert_eq!(listed.items[0].context_original_tokens, Some(52000));
+    assert_eq!(listed.items[0].context_compressed_tokens, Some(29800));
+}
+
 pub async fn test_log_batch_empty(ctx: &UsageTestContext<'_>) {
     // Empty batch should return 0 without error
     let result = ctx
@@ -1891,6 +2034,263 @@ pub async fn test_team_aggregation_excludes_other_teams(ctx: &UsageTestContext<'
     assert_eq!(summary_b.total_cost_microcents, 1000);
 }
 
+// ============================================================================
+// Anomaly Detection Query Tests
+// ============================================================================
+
+pub async fn test_get_hourly_spend_and_usage_mix(ctx: &UsageTestContext<'_>) {
+    let org_id = ctx.create_test_org("anomaly-org").await;
+    let api_key_id = ctx.create_test_api_key(org_id, "anomaly-key").await;
+    let now = Utc::now();
+    let entry =
+        |model: &str, referer: Option<&str>, cost: i64, at: chrono::DateTime<Utc>| UsageLogEntry {
+            http_referer: referer.map(String::from),
+            request_at: at,
+            ..create_attributed_usage_entry(
+                UsageAttribution {
+                    api_key_id: Some(api_key_id),
+                    org_id: Some(org_id),
+                    ..Default::default()
+                },
+                model,
+                "openai",
+                cost,
+            )
+        };
+
+    for e in [
+        entry("gpt-4", Some("https://app.example.com"), 100, now),
+        entry("gpt-4", Some("https://app.example.com"), 200, now),
+        entry("gpt-4o-mini", None, 50, now),
+        entry("gpt-4", None, 1000, now - Duration::hours(3)),
+        entry("gpt-4", None, 5000, now - Duration::days(3)),
+    ] {
+        ctx.usage_repo.log(e).await.expect("Failed to log usage");
+    }
+
+    let since = now - Duration::days(1);
+    let until = now + Duration::minutes(1);
+    let hourly = ctx
+        .usage_repo
+        .get_hourly_spend(since, until)
+        .await
+        .expect("Failed to get hourly spend");
+    assert_eq!(hourly.len(), 2);
+    assert!(hourly.iter().all(|h| h.api_key_id == Some(api_key_id)));
+    assert!(hourly.iter().all(|h| h.org_id == Some(org_id)));
+    assert_eq!(hourly[0].total_cost_microcents, 1000);
+    assert_eq!(hourly[1].total_cost_microcents, 350);
+    assert_eq!(hourly[1].request_count, 3);
+    assert!(hourly[0].hour < hourly[1].hour);
+    assert!(hourly[1].hour <= now && now - hourly[1].hour < Duration::hours(1));
+
+    let mix = ctx
+        .usage_repo
+        .get_usage_mix(now - Duration::hours(1), until)
+        .await
+        .expect("Failed to get usage mix");
+    assert_eq!(mix.len(), 2);
+    let app = mix
+        .iter()
+        .find(|m| m.referer.as_deref() == Some("https://app.example.com"))
+        .expect("app referer row");
+    assert_eq!(app.model, "gpt-4");
+    assert_eq!(app.request_count, 2);
+    assert_eq!(app.total_cost_microcents, 300);
+    let mini = mix
+        .iter()
+        .find(|m| m.model == "gpt-4o-mini")
+        .expect("mini row");
+    assert_eq!(mini.referer, None);
+    assert_eq!(mini.total_cost_microcents, 50);
+}
+
+pub async fn test_get_daily_provider_attempts(ctx: &UsageTestContext<'_>) {
+    let org_id = ctx.create_test_org("reconcile-org").await;
+    let api_key_id = ctx.create_test_api_key(org_id, "reconcile-key").await;
+    let entry = |model: &str, attempts: Option<i32>, timed_out: Option<i32>| UsageLogEntry {
+        provider_attempts: attempts,
+        suspected_duplicate_attempts: timed_out,
+        ..create_usage_entry(api_key_id, model, "openai", 100, 50, Some(1000))
+    };
+
+    for e in [
+        entry("gpt-4", Some(1), Some(0)),
+        entry("gpt-4", Some(3), Some(2)),
+        // Recorded without attempt counts, e.g. a cache hit
+        entry("gpt-4", None, None),
+        entry("gpt-4o-mini", Some(1), Some(0)),
+        UsageLogEntry {
+            record_type: "tool".to_string(),
+            ..entry("gpt-4", Some(1), Some(1))
+        },
+    ] {
+        ctx.usage_repo.log(e).await.expect("Failed to log usage");
+    }
+
+    let rows = ctx
+        .usage_repo
+        .get_daily_provider_attempts(today_range())
+        .await
+        .expect("Failed to get provider attempts");
+    assert_eq!(rows.len(), 2);
+    let gpt4 = &rows[0];
+    assert_eq!(gpt4.model, "gpt-4");
+    assert_eq!(gpt4.request_count, 3);
+    assert_eq!(gpt4.provider_attempts, 4);
+    assert_eq!(gpt4.suspected_duplicate_attempts, 2);
+    assert_eq!(gpt4.cost_microcents, 3000);
+    assert_eq!(rows[1].model, "gpt-4o-mini");
+    assert_eq!(rows[1].provider_attempts, 1);
+}
+
+pub async fn test_get_daily_reliability(ctx: &UsageTestContext<'_>) {
+    let org_id = ctx.create_test_org("reliability-org").await;
+    let other_org_id = ctx.create_test_org("reliability-other").await;
+    let api_key_id = ctx.create_test_api_key(org_id, "reliability-key").await;
+    let entry =
+        |latency_ms: Option<i32>, error_code: Option<&str>, cached_tokens: i32| UsageLogEntry {
+            org_id: Some(org_id),
+            latency_ms,
+            error_code: error_code.map(String::from),
+            cached_tokens,
+            ..create_usage_entry(api_key_id, "gpt-4", "openai", 100, 50, Some(1000))
+        };
+
+    for e in [
+        entry(Some(80), None, 0),
+        entry(Some(300), None, 64),
+        entry(Some(4_000), Some("timeout"), 0),
+        entry(None, Some("rate_limited"), 0),
+        entry(Some(150), Some("content_filtered"), 0),
+        entry(Some(200), Some("context_length"), 0),
+        UsageLogEntry {
+            org_id: Some(other_org_id),
+            ..entry(Some(100), None, 0)
+        },
+        UsageLogEntry {
+            record_type: "tool".to_string(),
+            ..entry(Some(100), Some("provider_error"), 0)
+        },
+    ] {
+        ctx.usage_repo.log(e).await.expect("Failed to log usage");
+    }
+
+    let rows = ctx
+        .usage_repo
+        .get_daily_reliability(today_range(), Some(org_id), None)
+        .await
+        .expect("Failed to get reliability");
+    assert_eq!(rows.len(), 1);
+    let row = &rows[0];
+    assert_eq!(row.model, "gpt-4");
+    assert_eq!(row.project_id, None);
+    assert_eq!(row.request_count, 6);
+    assert_eq!(row.error_count, 4);
+    assert_eq!(row.provider_errors, 1);
+    assert_eq!(row.guardrail_blocks, 1);
+    assert_eq!(row.rate_limited, 1);
+    assert_eq!(row.cache_hits, 1);
+    assert_eq!(row.latency_count, 5);
+    // Bounds start 100, 250, 500, 1000, 2500, 5000
+    assert_eq!(&row.latency_buckets[..6], &[1, 3, 4, 4, 4, 5]);
+
+    let all = ctx
+        .usage_repo
+        .get_daily_reliability(today_range(), None, None)
+        .await
+        .expect("Failed to get reliability");
+    assert_eq!(all[0].request_count, 7);
+}
+
+pub async fn test_get_tag_usage(ctx: &UsageTestContext<'_>) {
+    use std::collections::BTreeMap;
+
+    use crate::db::repos::UsageLogQuery;
+    let org_id = ctx.create_test_org("tags-org").await;
+    let other_org_id = ctx.create_test_org("tags-other").await;
+    let api_key_id = ctx.create_test_api_key(org_id, "tags-key").await;
+    let entry = |org_id: Uuid, tags: &[(&str, &str)], cost: i64| UsageLogEntry {
+        org_id: Some(org_id),
+        tags: (!tags.is_empty()).then(|| {
+            tags.iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<BTreeMap<_, _>>()
+        }),
+        ..create_usage_entry(api_key_id, "gpt-4", "openai", 100, 50, Some(cost))
+    };
+
+    ctx.usage_repo
+        .log_batch(vec![
+            entry(
+                org_id,
+                &[("team", "search"), ("feature", "autocomplete")],
+                1000,
+            ),
+            entry(org_id, &[("feature", "autocomplete")], 2000),
+            entry(org_id, &[("feature", "chat")], 1500),
+            entry(org_id, &[("team", "search")], 500),
+            entry(org_id, &[], 250),
+            entry(other_org_id, &[("feature", "autocomplete")], 9000),
+        ])
+        .await
+        .expect("Failed to log batch");
+
+    let by_feature = ctx
+        .usage_repo
+        .get_tag_usage("feature", today_range(), Some(org_id), None)
+        .await
+        .expect("Failed to get tag usage");
+    assert_eq!(by_feature.len(), 3);
+    assert_eq!(by_feature[0].tag_value.as_deref(), Some("autocomplete"));
+    assert_eq!(by_feature[0].total_cost_microcents, 3000);
+    assert_eq!(by_feature[0].request_count, 2);
+    assert_eq!(by_feature[1].tag_value.as_deref(), Some("chat"));
+    // Requests without the tag are grouped together
+    assert_eq!(by_feature[2].tag_value, None);
+    assert_eq!(by_feature[2].request_count, 2);
+    assert_eq!(by_feature[2].total_cost_microcents, 750);
+
+    let all = ctx
+        .usage_repo
+        .get_tag_usage("feature", today_range(), None, None)
+        .await
+        .expect("Failed to get tag usage");
+    assert_eq!(all[0].total_cost_microcents, 12000);
+
+    let daily = ctx
+        .usage_repo
+        .get_daily_tag_usage("team", today_range(), Some(org_id), None)
+        .await
+        .expect("Failed to get daily tag usage");
+    assert_eq!(daily.len(), 2);
+    let search = daily
+        .iter()
+        .find(|d| d.tag_value.as_deref() == Some("search"))
+        .expect("search tag missing");
+    assert_eq!(search.date, Utc::now().date_naive());
+    assert_eq!(search.request_count, 2);
+    assert_eq!(search.total_cost_microcents, 1500);
+
+    let listed = ctx
+        .usage_repo
+        .list_logs(UsageLogQuery {
+            org_id: Some(org_id),
+            limit: Some(10),
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to list logs");
+    assert_eq!(listed.items.len(), 5);
+    assert_eq!(listed.items.iter().filter(|r| r.tags.is_none()).count(), 1);
+    assert!(listed.items.iter().any(|r| {
+        r.tags.as_ref().is_some_and(|t| {
+            t.get("team").map(String::as_str) == Some("search")
+                && t.get("feature").map(String::as_str) == Some("autocomplete")
+        })
+    }));
+}
+
 // ============================================================================
 // SQLite Tests - Fast, in-memory
 // ============================================================================
@@ -1935,6 +2335,9 @@ mod sqlite_tests {
     sqlite_test!(test_log_with_referer);
     sqlite_test!(test_log_multiple_entries);
     sqlite_test!(test_log_tool_runtime_seconds_round_trip);
+    sqlite_test!(test_log_error_code_filter);
+    sqlite_test!(test_log_structured_output_repairs);
+    sqlite_test!(test_log_context_compression_tokens);
 
     // Log batch tests
     sqlite_test!(test_log_batch_empty);
@@ -1999,6 +2402,18 @@ mod sqlite_tests {
     sqlite_test!(test_get_model_usage_by_team);
     sqlite_test!(test_get_provider_usage_by_team);
     sqlite_test!(test_team_aggregation_excludes_other_teams);
+
+    // Anomaly detection query tests
+    sqlite_test!(test_get_hourly_spend_and_usage_mix);
+
+    // Reconciliation query tests
+    sqlite_test!(test_get_daily_provider_attempts);
+
+    // Reliability query tests
+    sqlite_test!(test_get_daily_reliability);
+
+    // Tag query tests
+    sqlite_test!(test_get_tag_usage);
 }
 
 // ============================================================================
@@ -2040,6 +2455,9 @@ mod postgres_tests {
     postgres_test!(test_log_with_referer);
     postgres_test!(test_log_multiple_entries);
     postgres_test!(test_log_tool_runtime_seconds_round_trip);
+    postgres_test!(test_log_error_code_filter);
+    postgres_test!(test_log_structured_output_repairs);
+    postgres_test!(test_log_context_compression_tokens);
 
     // Log batch tests
     postgres_test!(test_log_batch_empty);
@@ -2104,4 +2522,16 @@ mod postgres_tests {
     postgres_test!(test_get_model_usage_by_team);
     postgres_test!(test_get_provider_usage_by_team);
     postgres_test!(test_team_aggregation_excludes_other_teams);
+
+    // Anomaly detection query tests
+    postgres_test!(test_get_hourly_spend_and_usage_mix);
+
+    // Reconciliation query tests
+    postgres_test!(test_get_daily_provider_attempts);
+
+    // Reliability query tests
+    postgres_test!(test_get_daily_reliability);
+
+    // Tag query tests
+    postgres_test!(test_get_tag_usage);
 }
@@ -0,0 +1,244 @@
+//! Shared tests for UsageAnomalyRepo implementations
+
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::{
+    db::repos::{ListParams, UsageAnomalyRepo},
+    models::{AnomalyKind, CreateUsageAnomaly, UsageAnomalyFilter},
+};
+
+fn anomaly_input(kind: AnomalyKind, org_id: Uuid, api_key_id: Option<Uuid>) -> CreateUsageAnomaly {
+    let now = Utc::now();
+    CreateUsageAnomaly {
+        kind,
+        org_id: Some(org_id),
+        api_key_id,
+        detail: None,
+        summary: "Spend of $12.00 in the last hour against a baseline of $0.50".to_string(),
+        observed: 12_000_000.0,
+        expected: 500_000.0,
+        score: 9.5,
+        window_start: now - Duration::hours(1),
+        window_end: now,
+        fingerprint: format!("{}:{}", kind.as_str(), api_key_id.unwrap_or(org_id)),
+        rate_limited_until: None,
+    }
+}
+
+pub async fn create_get_and_list(repo: &dyn UsageAnomalyRepo) {
+    let org_id = Uuid::new_v4();
+    let api_key_id = Uuid::new_v4();
+    let until = Utc::now() + Duration::minutes(15);
+    let created = repo
+        .create(CreateUsageAnomaly {
+            rate_limited_until: Some(until),
+            ..anomaly_input(AnomalyKind::SpendSpike, org_id, Some(api_key_id))
+        })
+        .await
+        .expect("create anomaly");
+
+    let fetched = repo
+        .get_by_id(created.id)
+        .await
+        .expect("get anomaly")
+        .expect("anomaly exists");
+    assert_eq!(fetched.kind, AnomalyKind::SpendSpike);
+    assert_eq!(fetched.org_id, Some(org_id));
+    assert_eq!(fetched.api_key_id, Some(api_key_id));
+    assert_eq!(fetched.score, 9.5);
+    assert!(fetched.rate_limited_until.is_some());
+    assert!(fetched.acknowledged_at.is_none());
+    assert!(repo.get_by_id(Uuid::new_v4()).await.unwrap().is_none());
+
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    repo.create(CreateUsageAnomaly {
+        detail: Some("https://unknown.example.com".to_string()),
+        ..anomaly_input(AnomalyKind::NewReferer, org_id, None)
+    })
+    .await
+    .expect("create org anomaly");
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    repo.create(anomaly_input(
+        AnomalyKind::ModelMix,
+        Uuid::new_v4(),
+        Some(Uuid::new_v4()),
+    ))
+    .await
+    .expect("create other org anomaly");
+
+    let all = repo
+        .list(&UsageAnomalyFilter::default(), ListParams::default())
+        .await
+        .expect("list all");
+    assert_eq!(all.items.len(), 3);
+    assert_eq!(all.items[0].kind, AnomalyKind::ModelMix);
+
+    let first = repo
+        .list(
+            &UsageAnomalyFilter::default(),
+            ListParams {
+                limit: Some(2),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("list first page");
+    assert_eq!(first.items.len(), 2);
+    assert!(first.has_more);
+
+    let by_org = repo
+        .list(
+            &UsageAnomalyFilter {
+                org_id: Some(org_id),
+                ..Default::default()
+            },
+            ListParams::default(),
+        )
+        .await
+        .expect("list by org");
+    assert_eq!(by_org.items.len(), 2);
+    assert_eq!(by_org.items[0].kind, AnomalyKind::NewReferer);
+    assert_eq!(
+        by_org.items[0].detail.as_deref(),
+        Some("https://unknown.example.com")
+    );
+
+    let by_key_and_kind = repo
+        .list(
+            &UsageAnomalyFilter {
+                api_key_id: Some(api_key_id),
+                kind: Some(AnomalyKind::SpendSpike),
+                ..Default::default()
+            },
+            ListParams::default(),
+        )
+        .await
+        .expect("list by key and kind");
+    assert_eq!(by_key_and_kind.items.len(), 1);
+    assert_eq!(by_key_and_kind.items[0].id, created.id);
+}
+
+pub async fn exists_since_and_acknowledge(repo: &dyn UsageAnomalyRepo) {
+    let org_id = Uuid::new_v4();
+    let input = anomaly_input(AnomalyKind::SpendSpike, org_id, None);
+    let fingerprint = input.fingerprint.clone();
+    assert!(
+        !repo
+            .exists_since(&fingerprint, Utc::now() - Duration::hours(1))
+            .await
+            .unwrap()
+    );
+
+    let created = repo.create(input).await.expect("create anomaly");
+    assert!(
+        repo.exists_since(&fingerprint, Utc::now() - Duration::hours(1))
+            .await
+            .unwrap()
+    );
+    assert!(
+        !repo
+            .exists_since(&fingerprint, Utc::now() + Duration::minutes(1))
+            .await
+            .unwrap()
+    );
+    assert!(
+        !repo
+            .exists_since("model_mix:other", Utc::now() - Duration::hours(1))
+            .await
+            .unwrap()
+    );
+
+    let user_id = Uuid::new_v4();
+    let acknowledged = repo
+        .acknowledge(created.id, Some(user_id))
+        .await
+        .expect("acknowledge")
+        .expect("anomaly exists");
+    assert!(acknowledged.acknowledged_at.is_some());
+    assert_eq!(acknowledged.acknowledged_by, Some(user_id));
+
+    // A second acknowledgement keeps the first
+    let again = repo
+        .acknowledge(created.id, Some(Uuid::new_v4()))
+        .await
+        .expect("acknowledge again")
+        .expect("anomaly exists");
+    assert_eq!(again.acknowledged_by, Some(user_id));
+    assert_eq!(again.acknowledged_at, acknowledged.acknowledged_at);
+
+    assert!(
+        repo.acknowledge(Uuid::new_v4(), None)
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    let open = repo
+        .list(
+            &UsageAnomalyFilter {
+                org_id: Some(org_id),
+                unacknowledged: true,
+                ..Default::default()
+            },
+            ListParams::default(),
+        )
+        .await
+        .expect("list unacknowledged");
+    assert!(open.items.is_empty());
+}
+
+#[cfg(all(test, feature = "database-sqlite"))]
+mod sqlite_tests {
+    use crate::db::{
+        sqlite::SqliteUsageAnomalyRepo,
+        tests::harness::{create_sqlite_pool, run_sqlite_migrations},
+    };
+
+    async fn create_repo() -> SqliteUsageAnomalyRepo {
+        let pool = create_sqlite_pool().await;
+        run_sqlite_migrations(&pool).await;
+        SqliteUsageAnomalyRepo::new(pool)
+    }
+
+    macro_rules! sqlite_test {
+        ($name:ident) => {
+            #[tokio::test]
+            async fn $name() {
+                let repo = create_repo().await;
+                super::$name(&repo).await;
+            }
+        };
+    }
+
+    sqlite_test!(create_get_and_list);
+    sqlite_test!(exists_since_and_acknowledge);
+}
+
+#[cfg(all(test, feature = "database-postgres"))]
+mod postgres_tests {
+    use crate::db::{
+        postgres::PostgresUsageAnomalyRepo,
+        tests::harness::postgres::{create_isolated_postgres_pool, run_postgres_migrations},
+    };
+
+    async fn create_repo() -> PostgresUsageAnomalyRepo {
+        let pool = create_isolated_postgres_pool().await;
+        run_postgres_migrations(&pool).await;
+        PostgresUsageAnomalyRepo::new(pool, None)
+    }
+
+    macro_rules! postgres_test {
+        ($name:ident) => {
+            #[tokio::test]
+            #[ignore = "Requires Docker - run with `cargo test -- --ignored`"]
+            async fn $name() {
+                let repo = create_repo().await;
+                super::$name(&repo).await;
+            }
+        };
+    }
+
+    postgres_test!(create_get_and_list);
+    postgres_test!(exists_since_and_acknowledge);
+}
@@ -508,6 +508,41 @@ pub async fn test_count_org_members(ctx: &UserTestContext<'_>) {
     assert_eq!(count, 3);
 }
 
+pub async fn test_list_org_member_emails(ctx: &UserTestContext<'_>) {
+    let org_id = ctx.create_test_org("test-org").await;
+
+    for (external_id, email, role) in [
+        ("owner", Some("owner@example.com"), "owner"),
+        ("admin", Some("admin@example.com"), "admin"),
+        ("admin-no-email", None, "admin"),
+        ("member", Some("member@example.com"), "member"),
+    ] {
+        let user = ctx
+            .user_repo
+            .create(create_user_input(external_id, email, None))
+            .await
+            .expect("Failed to create user");
+        ctx.user_repo
+            .add_to_org(user.id, org_id, role, MembershipSource::Manual)
+            .await
+            .expect("Failed to add user to org");
+    }
+
+    let emails = ctx
+        .user_repo
+        .list_org_member_emails(org_id, &["owner".to_string(), "admin".to_string()])
+        .await
+        .expect("Failed to list member emails");
+    assert_eq!(emails, vec!["admin@example.com", "owner@example.com"]);
+
+    let none = ctx
+        .user_repo
+        .list_org_member_emails(org_id, &[])
+        .await
+        .expect("Failed to list member emails");
+    assert!(none.is_empty());
+}
+
 pub async fn test_org_members_isolated_by_org(ctx: &UserTestContext<'_>) {
     let org_id_1 = ctx.create_test_org("org-1").await;
     let org_id_2 = ctx.create_test_org("org-2").await;
@@ -1104,6 +1139,7 @@ mod sqlite_tests {
     sqlite_test!(test_remove_from_org_not_member);
     sqlite_test!(test_list_org_members_with_pagination);
     sqlite_test!(test_count_org_members);
+    sqlite_test!(test_list_org_member_emails);
     sqlite_test!(test_org_members_isolated_by_org);
 
     // Project membership tests
@@ -1185,6 +1221,7 @@ mod postgres_tests {
     postgres_test!(test_remove_from_org_not_member);
     postgres_test!(test_list_org_members_with_pagination);
     postgres_test!(test_count_org_members);
+    postgres_test!(test_list_org_member_emails);
     postgres_test!(test_org_members_isolated_by_org);
 
     // Project membership tests
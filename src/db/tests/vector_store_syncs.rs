@@ -0,0 +1,312 @@
+//! Shared tests for VectorStoreSyncRepo implementations
+
+use uuid::Uuid;
+
+use crate::{
+    db::repos::{ListParams, VectorStoreSyncRepo},
+    models::{
+        CreateVectorStore, CreateVectorStoreSync, VectorStoreOwner, VectorStoreSyncCounts,
+        VectorStoreSyncItem, VectorStoreSyncStatus,
+    },
+};
+
+fn store_input(org_id: Uuid) -> CreateVectorStore {
+    CreateVectorStore {
+        owner: VectorStoreOwner::Organization {
+            organization_id: org_id,
+        },
+        file_ids: vec![],
+        name: Some("Handbook".to_string()),
+        description: None,
+        embedding_model: "text-embedding-3-small".to_string(),
+        embedding_dimensions: 1536,
+        metadata: None,
+        expires_after: None,
+        hybrid_search: None,
+        chunking_strategy: None,
+    }
+}
+
+fn sync_input(vector_store_id: Uuid, source_name: &str) -> CreateVectorStoreSync {
+    CreateVectorStoreSync {
+        vector_store_id,
+        source_name: source_name.to_string(),
+        connector: "git".to_string(),
+    }
+}
+
+pub async fn create_finish_and_get(repo: &dyn VectorStoreSyncRepo, vector_store_id: Uuid) {
+    let created = repo
+        .create(sync_input(vector_store_id, "docs"))
+        .await
+        .expect("create sync");
+    assert_eq!(created.status, VectorStoreSyncStatus::Running);
+    assert!(created.finished_at.is_none());
+
+    let counts = VectorStoreSyncCounts {
+        files_added: 3,
+        files_updated: 1,
+        files_deleted: 2,
+        files_unchanged: 10,
+        files_failed: 1,
+    };
+    repo.finish(
+        created.id,
+        VectorStoreSyncStatus::PartiallySucceeded,
+        &counts,
+        Some("guide.pdf: too large"),
+    )
+    .await
+    .expect("finish sync");
+
+    let fetched = repo
+        .get_by_id(created.id)
+        .await
+        .expect("get sync")
+        .expect("sync exists");
+    assert_eq!(fetched.status, VectorStoreSyncStatus::PartiallySucceeded);
+    assert_eq!(fetched.connector, "git");
+    assert_eq!(fetched.files_added, 3);
+    assert_eq!(fetched.files_updated, 1);
+    assert_eq!(fetched.files_deleted, 2);
+    assert_eq!(fetched.files_unchanged, 10);
+    assert_eq!(fetched.files_failed, 1);
+    assert_eq!(fetched.error.as_deref(), Some("guide.pdf: too large"));
+    assert!(fetched.finished_at.is_some());
+
+    assert!(repo.get_by_id(Uuid::new_v4()).await.unwrap().is_none());
+    assert!(
+        repo.finish(
+            Uuid::new_v4(),
+            VectorStoreSyncStatus::Failed,
+            &VectorStoreSyncCounts::default(),
+            None,
+        )
+        .await
+        .is_err(),
+        "finishing an unknown run is an error"
+    );
+}
+
+pub async fn latest_and_list(repo: &dyn VectorStoreSyncRepo, vector_store_id: Uuid) {
+    assert!(
+        repo.latest_for_source(vector_store_id, "docs")
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    let mut ids = Vec::new();
+    for _ in 0..3 {
+        let sync = repo
+            .create(sync_input(vector_store_id, "docs"))
+            .await
+            .expect("create sync");
+        ids.push(sync.id);
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    }
+    repo.create(sync_input(vector_store_id, "wiki"))
+        .await
+        .expect("create other source");
+
+    let latest = repo
+        .latest_for_source(vector_store_id, "docs")
+        .await
+        .expect("latest")
+        .expect("latest exists");
+    assert_eq!(latest.id, ids[2]);
+
+    let first = repo
+        .list(
+            vector_store_id,
+            ListParams {
+                limit: Some(3),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("list first page");
+    assert_eq!(first.items.len(), 3);
+    assert!(first.has_more);
+    assert_eq!(first.items[0].source_name, "wiki", "newest first");
+
+    let second = repo
+        .list(
+            vector_store_id,
+            ListParams {
+                limit: Some(3),
+                cursor: first.cursors.next.clone(),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("list second page");
+    assert_eq!(second.items.len(), 1);
+    assert!(!second.has_more);
+    assert_eq!(second.items[0].id, ids[0]);
+
+    let other = repo
+        .list(Uuid::new_v4(), ListParams::default())
+        .await
+        .expect("list other store");
+    assert!(other.items.is_empty());
+}
+
+pub async fn items_upsert_and_delete(repo: &dyn VectorStoreSyncRepo, vector_store_id: Uuid) {
+    let first_file = Uuid::new_v4();
+    repo.upsert_item(
+        vector_store_id,
+        "docs",
+        &VectorStoreSyncItem {
+            source_key: "guide/intro.md".to_string(),
+            version: "abc".to_string(),
+            file_id: first_file,
+        },
+    )
+    .await
+    .expect("insert item");
+    repo.upsert_item(
+        vector_store_id,
+        "docs",
+        &VectorStoreSyncItem {
+            source_key: "guide/setup.md".to_string(),
+            version: "def".to_string(),
+            file_id: Uuid::new_v4(),
+        },
+    )
+    .await
+    .expect("insert second item");
+
+    let replacement = Uuid::new_v4();
+    repo.upsert_item(
+        vector_store_id,
+        "docs",
+        &VectorStoreSyncItem {
+            source_key: "guide/intro.md".to_string(),
+            version: "abd".to_string(),
+            file_id: replacement,
+        },
+    )
+    .await
+    .expect("update item");
+
+    let mut items = repo
+        .list_items(vector_store_id, "docs")
+        .await
+        .expect("list items");
+    items.sort_by(|a, b| a.source_key.cmp(&b.source_key));
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].version, "abd");
+    assert_eq!(items[0].file_id, replacement);
+
+    assert!(
+        repo.list_items(vector_store_id, "wiki")
+            .await
+            .unwrap()
+            .is_empty(),
+        "items are scoped to their source"
+    );
+
+    repo.delete_item(vector_store_id, "docs", "guide/setup.md")
+        .await
+        .expect("delete item");
+    let items = repo
+        .list_items(vector_store_id, "docs")
+        .await
+        .expect("list items after delete");
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].source_key, "guide/intro.md");
+}
+
+#[cfg(all(test, feature = "database-sqlite"))]
+mod sqlite_tests {
+    use uuid::Uuid;
+
+    use crate::{
+        db::{
+            repos::{OrganizationRepo, VectorStoresRepo},
+            sqlite::{SqliteOrganizationRepo, SqliteVectorStoreSyncRepo, SqliteVectorStoresRepo},
+            tests::harness::{create_sqlite_pool, run_sqlite_migrations},
+        },
+        models::CreateOrganization,
+    };
+
+    async fn create_repo() -> (SqliteVectorStoreSyncRepo, Uuid) {
+        let pool = create_sqlite_pool().await;
+        run_sqlite_migrations(&pool).await;
+        let org = SqliteOrganizationRepo::new(pool.clone())
+            .create(CreateOrganization {
+                slug: "acme".to_string(),
+                name: "Acme".to_string(),
+            })
+            .await
+            .expect("create org");
+        let store = SqliteVectorStoresRepo::new(pool.clone())
+            .create_vector_store(super::store_input(org.id))
+            .await
+            .expect("create vector store");
+        (SqliteVectorStoreSyncRepo::new(pool), store.id)
+    }
+
+    macro_rules! sqlite_test {
+        ($name:ident) => {
+            #[tokio::test]
+            async fn $name() {
+                let (repo, vector_store_id) = create_repo().await;
+                super::$name(&repo, vector_store_id).await;
+            }
+        };
+    }
+
+    sqlite_test!(create_finish_and_get);
+    sqlite_test!(latest_and_list);
+    sqlite_test!(items_upsert_and_delete);
+}
+
+#[cfg(all(test, feature = "database-postgres"))]
+mod postgres_tests {
+    use uuid::Uuid;
+
+    use crate::{
+        db::{
+            postgres::{
+                PostgresOrganizationRepo, PostgresVectorStoreSyncRepo, PostgresVectorStoresRepo,
+            },
+            repos::{OrganizationRepo, VectorStoresRepo},
+            tests::harness::postgres::{create_isolated_postgres_pool, run_postgres_migrations},
+        },
+        models::CreateOrganization,
+    };
+
+    async fn create_repo() -> (PostgresVectorStoreSyncRepo, Uuid) {
+        let pool = create_isolated_postgres_pool().await;
+        run_postgres_migrations(&pool).await;
+        let org = PostgresOrganizationRepo::new(pool.clone(), None)
+            .create(CreateOrganization {
+                slug: "acme".to_string(),
+                name: "Acme".to_string(),
+            })
+            .await
+            .expect("create org");
+        let store = PostgresVectorStoresRepo::new(pool.clone(), None)
+            .create_vector_store(super::store_input(org.id))
+            .await
+            .expect("create vector store");
+        (PostgresVectorStoreSyncRepo::new(pool, None), store.id)
+    }
+
+    macro_rules! postgres_test {
+        ($name:ident) => {
+            #[tokio::test]
+            #[ignore = "Requires Docker - run with `cargo test -- --ignored`"]
+            async fn $name() {
+                let (repo, vector_store_id) = create_repo().await;
+                super::$name(&repo, vector_store_id).await;
+            }
+        };
+    }
+
+    postgres_test!(create_finish_and_get);
+    postgres_test!(latest_and_list);
+    postgres_test!(items_upsert_and_delete);
+}
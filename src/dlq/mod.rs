@@ -5,6 +5,7 @@ mod error;
 mod file;
 #[cfg(feature = "redis")]
 mod redis;
+pub mod redrive;
 pub mod traits;
 pub mod worker;
 
@@ -17,8 +18,9 @@ pub use error::{DlqError, DlqResult};
 pub use file::FileDlq;
 #[cfg(feature = "redis")]
 pub use redis::RedisDlq;
+pub use redrive::start_dlq_redrive_worker;
 pub use traits::{DeadLetterQueue, DlqCursor, DlqCursorDirection, DlqEntry, DlqListParams};
-pub use worker::start_dlq_worker;
+pub use worker::{DlqFailureAlerts, start_dlq_worker};
 
 use crate::{config::DeadLetterQueueConfig, db::DbPool};
 
@@ -0,0 +1,575 @@
+//! Redrive of DLQ entries outside the retry worker's backoff schedule.
+//!
+//! Entries are redriven one at a time through the admin API, in batches
+//! matching a [`DlqRedriveFilter`], or by the scheduled policies configured
+//! under `observability.dead_letter_queue.redrive_policies`. Payloads can be
+//! edited or patched before they're replayed; the stored entry is only
+//! removed once the replay succeeds, and is otherwise left unchanged apart
+//! from its retry count.
+
+use std::{collections::HashMap, sync::Arc};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{
+    DeadLetterQueue, DlqCursorDirection, DlqEntry, DlqListParams, DlqResult, worker::process_entry,
+};
+use crate::{
+    config::{DlqErrorClass, DlqRedrivePolicy},
+    db::DbPool,
+    jobs::{
+        JobScheduler,
+        leader_lock::{self, LeadershipOutcome, keys},
+    },
+    models::{JobTrigger, UsageLogEntry},
+    observability::metrics,
+};
+
+/// Entries fetched per page while scanning the queue for matches.
+const SCAN_PAGE_SIZE: i64 = 500;
+
+/// Selects the DLQ entries to redrive. Every set field must match.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct DlqRedriveFilter {
+    /// Entry type (e.g., "usage_log").
+    pub entry_type: Option<String>,
+    /// Class of the entry's error.
+    pub error_class: Option<DlqErrorClass>,
+    /// Text the entry's error contains (case-insensitive).
+    pub error_contains: Option<String>,
+    /// Provider the failed operation was for.
+    pub provider: Option<String>,
+    /// Only entries created at or after this time.
+    pub created_after: Option<DateTime<Utc>>,
+    /// Only entries created before this time.
+    pub created_before: Option<DateTime<Utc>>,
+    /// Only entries retried fewer than this many times.
+    pub max_retries: Option<i32>,
+}
+
+impl DlqRedriveFilter {
+    /// The filter for a scheduled policy, with its age bounds resolved
+    /// against `now`.
+    pub fn for_policy(policy: &DlqRedrivePolicy, now: DateTime<Utc>) -> Self {
+        let ago = |secs: u64| now - Duration::seconds(secs as i64);
+        Self {
+            entry_type: policy.entry_type.clone(),
+            error_class: policy.error_class,
+            error_contains: policy.error_contains.clone(),
+            provider: policy.provider.clone(),
+            created_after: policy.max_age_secs.map(ago),
+            created_before: (policy.min_age_secs > 0).then(|| ago(policy.min_age_secs)),
+            max_retries: Some(policy.max_retries),
+        }
+    }
+
+    /// Whether `entry` matches every field set on the filter.
+    pub fn matches(&self, entry: &DlqEntry) -> bool {
+        if let Some(entry_type) = &self.entry_type
+            && entry.entry_type != *entry_type
+        {
+            return false;
+        }
+        if let Some(class) = self.error_class
+            && DlqErrorClass::of(&entry.error) != class
+        {
+            return false;
+        }
+        if let Some(text) = &self.error_contains
+            && !entry.error.to_lowercase().contains(&text.to_lowercase())
+        {
+            return false;
+        }
+        if let Some(after) = self.created_after
+            && entry.created_at < after
+        {
+            return false;
+        }
+        if let Some(before) = self.created_before
+            && entry.created_at >= before
+        {
+            return false;
+        }
+        if let Some(max_retries) = self.max_retries
+            && entry.retry_count >= max_retries
+        {
+            return false;
+        }
+        if let Some(provider) = &self.provider
+            && entry_provider(entry).as_deref() != Some(provider.as_str())
+        {
+            return false;
+        }
+        true
+    }
+}
+
+/// The provider an entry's operation was for, from its `provider` metadata
+/// or, failing that, a `provider` field in its JSON payload.
+fn entry_provider(entry: &DlqEntry) -> Option<String> {
+    if let Some(provider) = entry.metadata.get("provider") {
+        return Some(provider.clone());
+    }
+    let payload: serde_json::Value = serde_json::from_str(&entry.payload).ok()?;
+    payload.get("provider")?.as_str().map(str::to_string)
+}
+
+/// Apply a JSON merge patch (RFC 7386): objects merge recursively, `null`
+/// removes a field and anything else replaces the target.
+pub fn merge_patch(target: &mut serde_json::Value, patch: &serde_json::Value) {
+    let serde_json::Value::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = serde_json::Value::Object(serde_json::Map::new());
+    }
+    if let serde_json::Value::Object(target) = target {
+        for (key, value) in patch {
+            if value.is_null() {
+                target.remove(key);
+            } else {
+                merge_patch(
+                    target.entry(key.clone()).or_insert(serde_json::Value::Null),
+                    value,
+                );
+            }
+        }
+    }
+}
+
+/// A copy of `entry` with `patch` merged into its payload.
+pub fn patch_entry(entry: &DlqEntry, patch: &serde_json::Value) -> Result<DlqEntry, String> {
+    let mut payload: serde_json::Value = serde_json::from_str(&entry.payload)
+        .map_err(|e| format!("Payload is not JSON, so it can't be patched: {}", e))?;
+    merge_patch(&mut payload, patch);
+    Ok(DlqEntry {
+        payload: payload.to_string(),
+        ..entry.clone()
+    })
+}
+
+/// Whether entries of this type can be redriven. Must agree with the types
+/// handled by the retry worker.
+pub fn is_supported(entry_type: &str) -> bool {
+    entry_type == "usage_log"
+}
+
+/// Check that an entry's payload can be replayed, so a malformed edit is
+/// rejected before it counts as a retry.
+pub fn validate_payload(entry: &DlqEntry) -> Result<(), String> {
+    match entry.entry_type.as_str() {
+        "usage_log" => serde_json::from_str::<UsageLogEntry>(&entry.payload)
+            .map(|_| ())
+            .map_err(|e| format!("Invalid usage_log payload: {}", e)),
+        other => Err(format!("Unsupported entry type for redrive: {}", other)),
+    }
+}
+
+/// Outcome of redriving a single entry.
+#[derive(Debug)]
+pub enum RedriveOutcome {
+    /// Replayed and removed from the queue.
+    Succeeded,
+    /// Replay failed; the entry's retry count was bumped.
+    Failed(String),
+    /// Entries of this type can't be redriven.
+    Unsupported,
+}
+
+/// Replay `entry`, which may carry an edited payload, and remove the stored
+/// entry with the same ID if it succeeds. `source` labels the
+/// `dlq_redrives_total` metric.
+pub async fn redrive_entry(
+    dlq: &Arc<dyn DeadLetterQueue>,
+    db: &Arc<DbPool>,
+    entry: &DlqEntry,
+    source: &str,
+) -> RedriveOutcome {
+    match process_entry(entry, db).await {
+        Ok(true) => {
+            if let Err(e) = dlq.remove(entry.id).await {
+                tracing::error!(
+                    entry_id = %entry.id,
+                    error = %e,
+                    "Failed to remove redriven DLQ entry"
+                );
+            }
+            metrics::record_dlq_redrive(source, &entry.entry_type, "success");
+            RedriveOutcome::Succeeded
+        }
+        Ok(false) => RedriveOutcome::Unsupported,
+        Err(e) => {
+            if let Err(mark_err) = dlq.mark_retried(entry.id).await {
+                tracing::error!(
+                    entry_id = %entry.id,
+                    error = %mark_err,
+                    "Failed to mark DLQ entry as retried"
+                );
+            }
+            metrics::record_dlq_redrive(source, &entry.entry_type, "failure");
+            RedriveOutcome::Failed(e.to_string())
+        }
+    }
+}
+
+/// Result of redriving the entries matching a filter.
+#[derive(Debug, Default, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct RedriveSummary {
+    /// Entries matching the filter, up to the limit.
+    pub matched: usize,
+    /// Entries replayed and removed from the queue.
+    pub succeeded: usize,
+    /// Entries whose replay failed. They stay queued.
+    pub failed: usize,
+    /// Entries of a type that can't be redriven.
+    pub skipped: usize,
+    /// IDs of the matching entries, returned for dry runs.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub entry_ids: Vec<Uuid>,
+    /// Why each failed entry failed.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub failures: Vec<RedriveFailure>,
+}
+
+/// A failed redrive.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct RedriveFailure {
+    /// DLQ entry ID.
+    pub id: Uuid,
+    /// Error from the replay or the patch.
+    pub error: String,
+}
+
+/// Redrive up to `limit` entries matching `filter`, oldest first, merging
+/// `patch` into each payload. A dry run only counts the matches.
+pub async fn redrive_matching(
+    dlq: &Arc<dyn DeadLetterQueue>,
+    db: &Arc<DbPool>,
+    filter: &DlqRedriveFilter,
+    patch: Option<&serde_json::Value>,
+    limit: usize,
+    dry_run: bool,
+    source: &str,
+) -> DlqResult<RedriveSummary> {
+    // Collect matches before redriving, since successful redrives remove
+    // entries from the pages being scanned.
+    let entries = find_matching(dlq, filter, limit).await?;
+    let mut summary = RedriveSummary {
+        matched: entries.len(),
+        ..Default::default()
+    };
+    if dry_run {
+        summary.entry_ids = entries.iter().map(|e| e.id).collect();
+        return Ok(summary);
+    }
+
+    for entry in entries {
+        if !is_supported(&entry.entry_type) {
+            summary.skipped += 1;
+            continue;
+        }
+        let entry = match patch.map(|p| patch_entry(&entry, p)).transpose() {
+            Ok(patched) => patched.unwrap_or(entry),
+            Err(error) => {
+                summary.failed += 1;
+                summary.failures.push(RedriveFailure {
+                    id: entry.id,
+                    error,
+                });
+                continue;
+            }
+        };
+        match redrive_entry(dlq, db, &entry, source).await {
+            RedriveOutcome::Succeeded => summary.succeeded += 1,
+            RedriveOutcome::Failed(error) => {
+                summary.failed += 1;
+                summary.failures.push(RedriveFailure {
+                    id: entry.id,
+                    error,
+                });
+            }
+            RedriveOutcome::Unsupported => summary.skipped += 1,
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Page through the queue, oldest first, collecting up to `limit` matches.
+async fn find_matching(
+    dlq: &Arc<dyn DeadLetterQueue>,
+    filter: &DlqRedriveFilter,
+    limit: usize,
+) -> DlqResult<Vec<DlqEntry>> {
+    let mut matched = Vec::new();
+    let mut cursor = None;
+
+    loop {
+        let page = dlq
+            .list(DlqListParams {
+                entry_type: filter.entry_type.clone(),
+                limit: Some(SCAN_PAGE_SIZE),
+                older_than: filter.created_before,
+                max_retries: filter.max_retries,
+                cursor,
+                direction: DlqCursorDirection::Forward,
+            })
+            .await?;
+
+        matched.extend(page.items.into_iter().filter(|e| filter.matches(e)));
+        if matched.len() >= limit {
+            matched.truncate(limit);
+            break;
+        }
+        match page.cursors.next {
+            Some(next) if page.has_more => cursor = Some(next),
+            _ => break,
+        }
+    }
+
+    Ok(matched)
+}
+
+/// Starts the scheduled redrive worker for the configured policies.
+///
+/// All policies run under a single `dlq_redrive` job that ticks at the
+/// shortest policy interval; each tick runs the policies that are due, and a
+/// manual trigger runs all of them.
+pub async fn start_dlq_redrive_worker(
+    dlq: Arc<dyn DeadLetterQueue>,
+    db: Arc<DbPool>,
+    policies: Vec<DlqRedrivePolicy>,
+    scheduler: Arc<JobScheduler>,
+) {
+    let Some(interval_secs) = policies.iter().map(|p| p.interval_secs).min() else {
+        return;
+    };
+
+    tracing::info!(
+        policies = policies.len(),
+        interval_secs,
+        "Starting DLQ redrive worker"
+    );
+
+    let job = scheduler.register("dlq_redrive", std::time::Duration::from_secs(interval_secs));
+    let mut last_runs: HashMap<String, DateTime<Utc>> = HashMap::new();
+    let mut trigger = JobTrigger::Scheduled;
+
+    loop {
+        if job.should_run(trigger).await {
+            // One replica redrives per tick, so an entry isn't replayed twice
+            let guard = match leader_lock::try_acquire(&db, keys::DLQ_REDRIVE).await {
+                LeadershipOutcome::Leader(g) => Some(g),
+                LeadershipOutcome::NotLeader => {
+                    tracing::trace!("dlq_redrive: not leader this tick, skipping");
+                    trigger = job.tick().await;
+                    continue;
+                }
+                LeadershipOutcome::NoCoordination => None,
+            };
+
+            let now = Utc::now();
+            let due: Vec<&DlqRedrivePolicy> = policies
+                .iter()
+                .filter(|p| {
+                    trigger == JobTrigger::Manual
+                        || last_runs.get(&p.name).is_none_or(|last| {
+                            now - *last >= Duration::seconds(p.interval_secs as i64)
+                        })
+                })
+                .collect();
+            for policy in &due {
+                last_runs.insert(policy.name.clone(), now);
+            }
+
+            if let Err(e) = job
+                .run(trigger, run_policies(&dlq, &db, &due, now), |totals| {
+                    Some(format!(
+                        "Redrove {} of {} matching entries ({} failed)",
+                        totals.succeeded, totals.matched, totals.failed
+                    ))
+                })
+                .await
+            {
+                tracing::error!(error = %e, "Error running DLQ redrive policies");
+            }
+            drop(guard);
+        }
+
+        // Wait for the next interval or a manual trigger
+        trigger = job.tick().await;
+    }
+}
+
+/// Run each policy in turn, carrying on past failures. Returns the combined
+/// summary, or the errors if any policy couldn't scan the queue.
+async fn run_policies(
+    dlq: &Arc<dyn DeadLetterQueue>,
+    db: &Arc<DbPool>,
+    policies: &[&DlqRedrivePolicy],
+    now: DateTime<Utc>,
+) -> Result<RedriveSummary, String> {
+    let mut totals = RedriveSummary::default();
+    let mut errors = Vec::new();
+
+    for policy in policies {
+        let filter = DlqRedriveFilter::for_policy(policy, now);
+        let result = redrive_matching(
+            dlq,
+            db,
+            &filter,
+            policy.patch.as_ref(),
+            policy.batch_size as usize,
+            false,
+            &format!("policy:{}", policy.name),
+        )
+        .await;
+        match result {
+            Ok(summary) => {
+                if summary.matched > 0 {
+                    tracing::info!(
+                        policy = %policy.name,
+                        matched = summary.matched,
+                        succeeded = summary.succeeded,
+                        failed = summary.failed,
+                        skipped = summary.skipped,
+                        "DLQ redrive policy run complete"
+                    );
+                }
+                totals.matched += summary.matched;
+                totals.succeeded += summary.succeeded;
+                totals.failed += summary.failed;
+                totals.skipped += summary.skipped;
+            }
+            Err(e) => {
+                tracing::error!(policy = %policy.name, error = %e, "DLQ redrive policy failed");
+                errors.push(format!("{}: {}", policy.name, e));
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(totals)
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn entry(error: &str, payload: serde_json::Value) -> DlqEntry {
+        DlqEntry::new("usage_log", payload.to_string(), error)
+    }
+
+    #[test]
+    fn test_filter_matches() {
+        let e = entry(
+            "pool timed out while waiting for an open connection",
+            json!({"provider": "openai", "model": "gpt-4"}),
+        );
+
+        assert!(DlqRedriveFilter::default().matches(&e));
+        assert!(
+            DlqRedriveFilter {
+                entry_type: Some("usage_log".into()),
+                error_class: Some(DlqErrorClass::Timeout),
+                error_contains: Some("OPEN CONNECTION".into()),
+                provider: Some("openai".into()),
+                created_after: Some(e.created_at),
+                created_before: Some(e.created_at + Duration::seconds(1)),
+                max_retries: Some(1),
+            }
+            .matches(&e)
+        );
+
+        let miss = [
+            DlqRedriveFilter {
+                entry_type: Some("webhook".into()),
+                ..Default::default()
+            },
+            DlqRedriveFilter {
+                error_class: Some(DlqErrorClass::Constraint),
+                ..Default::default()
+            },
+            DlqRedriveFilter {
+                provider: Some("anthropic".into()),
+                ..Default::default()
+            },
+            DlqRedriveFilter {
+                created_before: Some(e.created_at),
+                ..Default::default()
+            },
+            DlqRedriveFilter {
+                max_retries: Some(0),
+                ..Default::default()
+            },
+        ];
+        for filter in miss {
+            assert!(!filter.matches(&e), "{filter:?} should not match");
+        }
+
+        // Metadata takes precedence over the payload
+        let e = e.with_metadata("provider", "azure");
+        assert_eq!(entry_provider(&e).as_deref(), Some("azure"));
+    }
+
+    #[test]
+    fn test_error_classes() {
+        let cases = [
+            (
+                "pool timed out while waiting for an open connection",
+                DlqErrorClass::Timeout,
+            ),
+            (
+                "error connecting to server: Connection refused",
+                DlqErrorClass::Connection,
+            ),
+            (
+                "UNIQUE constraint failed: usage_records.request_id",
+                DlqErrorClass::Constraint,
+            ),
+            (
+                "missing field `model` at line 1 column 2",
+                DlqErrorClass::Payload,
+            ),
+            (
+                "permanent delivery failure to https://example.com after retries",
+                DlqErrorClass::Other,
+            ),
+        ];
+        for (error, class) in cases {
+            assert_eq!(DlqErrorClass::of(error), class, "{error}");
+        }
+    }
+
+    #[test]
+    fn test_merge_patch() {
+        let mut target = json!({"model": "gpt-4", "provider": "openai", "tags": {"a": 1, "b": 2}});
+        merge_patch(
+            &mut target,
+            &json!({"provider": "azure", "tags": {"a": null, "c": 3}, "org_id": null}),
+        );
+        assert_eq!(
+            target,
+            json!({"model": "gpt-4", "provider": "azure", "tags": {"b": 2, "c": 3}})
+        );
+
+        let e = entry("error", json!({"provider": "openai"}));
+        let patched = patch_entry(&e, &json!({"provider": "azure"})).unwrap();
+        assert_eq!(patched.id, e.id);
+        assert_eq!(patched.payload, json!({"provider": "azure"}).to_string());
+
+        let raw = DlqEntry::new("usage_log", "not json", "error");
+        assert!(patch_entry(&raw, &json!({"provider": "azure"})).is_err());
+    }
+}
@@ -5,6 +5,9 @@
 //! 2. Attempts to reprocess them with exponential backoff
 //! 3. Removes successfully processed entries
 //! 4. Prunes old entries based on TTL
+//!
+//! Entries that use up their last retry are reported by email when
+//! `[notifications.dlq_failures]` is enabled.
 
 use std::sync::Arc;
 
@@ -14,8 +17,13 @@ use crate::{
     config::DlqRetryConfig,
     db::DbPool,
     dlq::{DeadLetterQueue, DlqEntry},
-    models::UsageLogEntry,
+    jobs::{
+        JobScheduler,
+        leader_lock::{self, LeadershipOutcome, keys},
+    },
+    models::{EmailKind, JobTrigger, UsageLogEntry},
     observability::metrics,
+    services::{EmailNotifier, Notification},
 };
 
 /// Starts the DLQ retry worker as a background task.
@@ -27,6 +35,8 @@ pub async fn start_dlq_worker(
     db: Arc<DbPool>,
     config: DlqRetryConfig,
     ttl_secs: u64,
+    scheduler: Arc<JobScheduler>,
+    failure_alerts: Option<DlqFailureAlerts>,
 ) {
     if !config.enabled {
         tracing::info!("DLQ retry worker disabled by configuration");
@@ -40,31 +50,63 @@ pub async fn start_dlq_worker(
         "Starting DLQ retry worker"
     );
 
-    let interval = std::time::Duration::from_secs(config.interval_secs);
+    let job = scheduler.register(
+        "dlq_retry",
+        std::time::Duration::from_secs(config.interval_secs),
+    );
+    let mut trigger = JobTrigger::Scheduled;
 
     loop {
-        // Process a batch of entries
-        if let Err(e) = process_batch(&dlq, &db, &config).await {
-            tracing::error!(error = %e, "Error processing DLQ batch");
-        }
+        if job.should_run(trigger).await {
+            // One replica retries per tick, so an entry isn't replayed twice
+            let guard = match leader_lock::try_acquire(&db, keys::DLQ_RETRY).await {
+                LeadershipOutcome::Leader(g) => Some(g),
+                LeadershipOutcome::NotLeader => {
+                    tracing::trace!("dlq_retry: not leader this tick, skipping");
+                    trigger = job.tick().await;
+                    continue;
+                }
+                LeadershipOutcome::NoCoordination => None,
+            };
+
+            // Process a batch of entries
+            if let Err(e) = job
+                .run(
+                    trigger,
+                    process_batch(&dlq, &db, &config, failure_alerts.as_ref()),
+                    |_| None,
+                )
+                .await
+            {
+                tracing::error!(error = %e, "Error processing DLQ batch");
+            }
 
-        // Prune old entries if enabled
-        if config.prune_enabled
-            && let Err(e) = prune_old_entries(&dlq, ttl_secs).await
-        {
-            tracing::error!(error = %e, "Error pruning old DLQ entries");
+            // Prune old entries if enabled
+            if config.prune_enabled
+                && let Err(e) = prune_old_entries(&dlq, ttl_secs).await
+            {
+                tracing::error!(error = %e, "Error pruning old DLQ entries");
+            }
+            drop(guard);
         }
 
-        // Wait for the next interval
-        tokio::time::sleep(interval).await;
+        // Wait for the next interval or a manual trigger
+        trigger = job.tick().await;
     }
 }
 
+/// Where to email entries that exhaust their retries.
+pub struct DlqFailureAlerts {
+    pub notifier: Arc<EmailNotifier>,
+    pub to: Vec<String>,
+}
+
 /// Process a batch of DLQ entries.
 async fn process_batch(
     dlq: &Arc<dyn DeadLetterQueue>,
     db: &Arc<DbPool>,
     config: &DlqRetryConfig,
+    failure_alerts: Option<&DlqFailureAlerts>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     // Get entries that are ready to be retried
     let params = crate::dlq::traits::DlqListParams {
@@ -139,6 +181,13 @@ async fn process_batch(
                         error = %e,
                         "Failed to process DLQ entry, will retry later"
                     );
+
+                    // That was the last retry; the entry stays until pruned or redriven
+                    if entry.retry_count + 1 >= config.max_retries
+                        && let Some(alerts) = failure_alerts
+                    {
+                        notify_exhausted(alerts, &entry, &e.to_string()).await;
+                    }
                 }
             }
         }
@@ -155,6 +204,33 @@ async fn process_batch(
     Ok(())
 }
 
+/// Email the configured addresses about an entry that won't be retried again.
+async fn notify_exhausted(alerts: &DlqFailureAlerts, entry: &DlqEntry, error: &str) {
+    let result = alerts
+        .notifier
+        .send(Notification {
+            kind: EmailKind::DlqFailure,
+            org: None,
+            to: alerts.to.clone(),
+            vars: vec![
+                ("entry_id", entry.id.to_string()),
+                ("entry_type", entry.entry_type.clone()),
+                ("retry_count", (entry.retry_count + 1).to_string()),
+                ("error", error.to_string()),
+                ("created_at", entry.created_at.to_rfc3339()),
+            ],
+        })
+        .await;
+
+    if let Err(e) = result {
+        tracing::warn!(
+            entry_id = %entry.id,
+            error = %e,
+            "Failed to send DLQ failure email"
+        );
+    }
+}
+
 /// Check if an entry is ready for retry based on exponential backoff.
 fn is_ready_for_retry(entry: &DlqEntry, config: &DlqRetryConfig) -> bool {
     // If never retried, check initial delay from creation
@@ -173,7 +249,7 @@ fn is_ready_for_retry(entry: &DlqEntry, config: &DlqRetryConfig) -> bool {
 ///
 /// Returns `Ok(true)` if successfully processed, `Ok(false)` if entry type
 /// is not supported, or `Err` if processing failed.
-async fn process_entry(
+pub(super) async fn process_entry(
     entry: &DlqEntry,
     db: &Arc<DbPool>,
 ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
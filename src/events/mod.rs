@@ -32,6 +32,8 @@ use serde::{Deserialize, Serialize};
 use tokio::sync::broadcast;
 use uuid::Uuid;
 
+use crate::models::AnomalyKind;
+
 /// Default channel capacity for the event bus.
 /// This determines how many events can be buffered before slow receivers
 /// start missing events (lagging).
@@ -51,6 +53,10 @@ pub enum EventTopic {
     Budget,
     /// Rate limiting events (warnings, exceeded)
     RateLimit,
+    /// API request lifecycle events (started, completed)
+    Requests,
+    /// Scheduled task events (failed runs)
+    Tasks,
     /// All events (wildcard subscription)
     All,
 }
@@ -143,6 +149,80 @@ pub enum ServerEvent {
         latency_ms: Option<u64>,
         error_message: Option<String>,
     },
+
+    /// An API request passed authentication and is being handled.
+    ///
+    /// Request events carry a sanitized summary only: no prompt or response
+    /// content, credentials or client addresses.
+    RequestStarted {
+        request_id: String,
+        timestamp: DateTime<Utc>,
+        method: String,
+        path: String,
+        org_id: Option<Uuid>,
+        project_id: Option<Uuid>,
+        user_id: Option<Uuid>,
+        api_key_id: Option<Uuid>,
+    },
+
+    /// An API request finished. For streamed responses this fires when the
+    /// response headers are sent, before token counts and cost are known.
+    RequestCompleted {
+        request_id: String,
+        timestamp: DateTime<Utc>,
+        method: String,
+        path: String,
+        model: Option<String>,
+        provider: Option<String>,
+        org_id: Option<Uuid>,
+        project_id: Option<Uuid>,
+        user_id: Option<Uuid>,
+        api_key_id: Option<Uuid>,
+        status_code: u16,
+        latency_ms: u64,
+        streamed: bool,
+        input_tokens: Option<i64>,
+        output_tokens: Option<i64>,
+        cost_microcents: Option<i64>,
+    },
+
+    /// Anomaly detection found usage departing from an API key's or
+    /// organization's baseline.
+    UsageAnomalyDetected {
+        anomaly_id: Uuid,
+        timestamp: DateTime<Utc>,
+        kind: AnomalyKind,
+        org_id: Option<Uuid>,
+        api_key_id: Option<Uuid>,
+        detail: Option<String>,
+        summary: String,
+        score: f64,
+        /// When the temporary rate limit applied to the API key lifts.
+        rate_limited_until: Option<DateTime<Utc>>,
+    },
+
+    /// A provider's health-based routing weight changed.
+    ProviderRoutingWeightChanged {
+        provider: String,
+        timestamp: DateTime<Utc>,
+        previous_weight: f64,
+        weight: f64,
+        /// `unhealthy`, `degraded`, or `recovering`.
+        reason: String,
+    },
+
+    /// A scheduled task run failed.
+    ScheduledTaskFailed {
+        task_id: Uuid,
+        run_id: Uuid,
+        timestamp: DateTime<Utc>,
+        org_id: Uuid,
+        name: String,
+        error: String,
+        consecutive_failures: i32,
+        /// Whether the task was disabled after this failure.
+        disabled: bool,
+    },
 }
 
 impl ServerEvent {
@@ -155,6 +235,11 @@ impl ServerEvent {
             ServerEvent::BudgetThresholdReached { .. } => EventTopic::Budget,
             ServerEvent::RateLimitWarning { .. } => EventTopic::RateLimit,
             ServerEvent::ProviderHealthChanged { .. } => EventTopic::Health,
+            ServerEvent::ProviderRoutingWeightChanged { .. } => EventTopic::Health,
+            ServerEvent::RequestStarted { .. } => EventTopic::Requests,
+            ServerEvent::RequestCompleted { .. } => EventTopic::Requests,
+            ServerEvent::UsageAnomalyDetected { .. } => EventTopic::Usage,
+            ServerEvent::ScheduledTaskFailed { .. } => EventTopic::Tasks,
         }
     }
 
@@ -167,6 +252,11 @@ impl ServerEvent {
             ServerEvent::BudgetThresholdReached { .. } => "budget_threshold_reached",
             ServerEvent::RateLimitWarning { .. } => "rate_limit_warning",
             ServerEvent::ProviderHealthChanged { .. } => "provider_health_changed",
+            ServerEvent::ProviderRoutingWeightChanged { .. } => "provider_routing_weight_changed",
+            ServerEvent::RequestStarted { .. } => "request_started",
+            ServerEvent::RequestCompleted { .. } => "request_completed",
+            ServerEvent::UsageAnomalyDetected { .. } => "usage_anomaly_detected",
+            ServerEvent::ScheduledTaskFailed { .. } => "scheduled_task_failed",
         }
     }
 }
@@ -592,6 +682,7 @@ mod tests {
             EventTopic::Health,
             EventTopic::Budget,
             EventTopic::RateLimit,
+            EventTopic::Requests,
             EventTopic::All,
         ];
 
@@ -665,6 +756,52 @@ mod tests {
                 latency_ms: Some(150),
                 error_message: None,
             },
+            ServerEvent::ProviderRoutingWeightChanged {
+                provider: "vertex".to_string(),
+                timestamp: Utc::now(),
+                previous_weight: 1.0,
+                weight: 0.0,
+                reason: "unhealthy".to_string(),
+            },
+            ServerEvent::RequestStarted {
+                request_id: "req-1".to_string(),
+                timestamp: Utc::now(),
+                method: "POST".to_string(),
+                path: "/v1/chat/completions".to_string(),
+                org_id: Some(Uuid::new_v4()),
+                project_id: None,
+                user_id: None,
+                api_key_id: Some(Uuid::new_v4()),
+            },
+            ServerEvent::RequestCompleted {
+                request_id: "req-1".to_string(),
+                timestamp: Utc::now(),
+                method: "POST".to_string(),
+                path: "/v1/chat/completions".to_string(),
+                model: Some("gpt-4".to_string()),
+                provider: Some("openai".to_string()),
+                org_id: Some(Uuid::new_v4()),
+                project_id: None,
+                user_id: None,
+                api_key_id: Some(Uuid::new_v4()),
+                status_code: 200,
+                latency_ms: 850,
+                streamed: false,
+                input_tokens: Some(100),
+                output_tokens: Some(50),
+                cost_microcents: Some(1000),
+            },
+            ServerEvent::UsageAnomalyDetected {
+                anomaly_id: Uuid::new_v4(),
+                timestamp: Utc::now(),
+                kind: AnomalyKind::SpendSpike,
+                org_id: Some(Uuid::new_v4()),
+                api_key_id: Some(Uuid::new_v4()),
+                detail: None,
+                summary: "Spend spike".to_string(),
+                score: 6.2,
+                rate_limited_until: Some(Utc::now()),
+            },
         ];
 
         for event in events {
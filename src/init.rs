@@ -7,6 +7,7 @@ use crate::{config, providers};
 /// This is a general-purpose helper for instantiating providers, used by:
 /// - Re-ranker initialization (via `AppState::create_reranker_provider`)
 /// - Provider health checker
+/// - Fine-tuning jobs (via `services::fine_tuning`)
 ///
 /// Returns an error message if the provider type is not supported.
 pub(crate) fn create_provider_instance(
@@ -208,8 +209,6 @@ pub(crate) async fn init_worker_embedding_service(
     Option<Arc<cache::EmbeddingService>>,
     Option<Arc<dyn cache::vector_store::VectorBackend>>,
 ) {
-    #[cfg(not(feature = "database-postgres"))]
-    let _ = &db;
     // Get embedding configuration (same priority as init_file_search_service)
     let file_search_config = match &config.features.file_search {
         Some(cfg) if cfg.enabled => cfg,
@@ -332,42 +331,86 @@ pub(crate) async fn init_worker_embedding_service(
 
                 Arc::new(store)
             }
+            #[cfg(feature = "database-sqlite")]
+            config::RagVectorBackend::Sqlite {
+                table_name,
+                distance_metric,
+            } => {
+                let sqlite_pool = match db.sqlite_pool() {
+                    Some(pool) => pool.clone(),
+                    None => {
+                        tracing::error!("SQLite vector backend requires SQLite database");
+                        return (Some(embedding_service), None);
+                    }
+                };
+
+                let store = cache::vector_store::SqliteVectorStore::new(
+                    sqlite_pool,
+                    table_name.trim_end_matches("_chunks").to_string(),
+                    embedding_config.dimensions,
+                    *distance_metric,
+                );
+
+                if let Err(e) = store.initialize().await {
+                    tracing::error!(error = %e, "Failed to initialize SQLite vector store");
+                    return (Some(embedding_service), None);
+                }
+
+                Arc::new(store)
+            }
+            #[cfg(not(feature = "database-sqlite"))]
+            config::RagVectorBackend::Sqlite { .. } => {
+                tracing::error!(
+                    "SQLite vector backend requires the 'database-sqlite' feature. \
+                         Rebuild with --features database-sqlite or use a different vector backend."
+                );
+                return (Some(embedding_service), None);
+            }
         }
     } else {
-        // Default to pgvector
-        #[cfg(not(feature = "database-postgres"))]
-        {
-            tracing::warn!(
-                "No vector store configured and the 'database-postgres' feature is not enabled. \
-                     Configure [features.file_search.vector_backend] or rebuild with --features database-postgres."
-            );
-            return (Some(embedding_service), None);
-        }
+        // Default to a "rag_chunks" table in the primary database
+        match db.pool() {
+            #[cfg(feature = "database-postgres")]
+            crate::db::DbPoolRef::Postgres(pools) => {
+                let store = cache::vector_store::PgvectorStore::new(
+                    pools.write.clone(),
+                    "rag".to_string(),
+                    embedding_config.dimensions,
+                    config::PgvectorIndexType::IvfFlat,
+                    config::DistanceMetric::default(), // Cosine (default)
+                );
 
-        #[cfg(feature = "database-postgres")]
-        {
-            let pg_pool = match db.pg_write_pool() {
-                Some(pool) => pool.clone(),
-                None => {
-                    tracing::warn!("No vector store configured and not using PostgreSQL");
+                if let Err(e) = store.initialize().await {
+                    tracing::error!(error = %e, "Failed to initialize pgvector store");
                     return (Some(embedding_service), None);
                 }
-            };
-
-            let store = cache::vector_store::PgvectorStore::new(
-                pg_pool,
-                "rag".to_string(),
-                embedding_config.dimensions,
-                config::PgvectorIndexType::IvfFlat,
-                config::DistanceMetric::default(), // Cosine (default)
-            );
 
-            if let Err(e) = store.initialize().await {
-                tracing::error!(error = %e, "Failed to initialize pgvector store");
-                return (Some(embedding_service), None);
+                Arc::new(store)
             }
+            #[cfg(feature = "database-sqlite")]
+            crate::db::DbPoolRef::Sqlite(pool) => {
+                let store = cache::vector_store::SqliteVectorStore::new(
+                    pool.clone(),
+                    "rag".to_string(),
+                    embedding_config.dimensions,
+                    config::DistanceMetric::default(),
+                );
+
+                if let Err(e) = store.initialize().await {
+                    tracing::error!(error = %e, "Failed to initialize SQLite vector store");
+                    return (Some(embedding_service), None);
+                }
 
-            Arc::new(store)
+                Arc::new(store)
+            }
+            #[allow(unreachable_patterns)]
+            _ => {
+                tracing::warn!(
+                    "No vector store configured and the database has no built-in vector backend. \
+                         Configure [features.file_search.vector_backend]."
+                );
+                return (Some(embedding_service), None);
+            }
         }
     };
 
@@ -0,0 +1,286 @@
+//! Access review reminder worker.
+//!
+//! Every `[notifications.access_review_reminders] interval_days`, each
+//! organization with stale users or API keys gets an email listing them,
+//! sent to its members holding the configured roles. Organizations with
+//! nothing stale are skipped.
+//!
+//! The last reminder is read back from the email send log, so the worker is
+//! restart-safe: a reminder is only due once `interval_days` have passed
+//! since the last one was sent.
+
+use std::{fmt::Write as _, sync::Arc, time::Duration as StdDuration};
+
+use chrono::{DateTime, Duration, Utc};
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    config::AccessReviewReminderNotifications,
+    db::{DbPool, ListParams},
+    jobs::leader_lock::{self, LeadershipOutcome, keys},
+    models::{EmailKind, EmailLogFilter, EmailSendStatus, Organization, StaleAccessResponse},
+    services::{AccessReviewService, EmailNotifier, Notification, NotifyError},
+};
+
+/// How often to look for organizations that are due. Much shorter than
+/// `interval_days`, so a reminder goes out soon after it becomes due.
+const CHECK_INTERVAL: StdDuration = StdDuration::from_secs(3600);
+
+/// Stale entries listed per section of the email; the rest are counted.
+const MAX_LISTED: usize = 20;
+
+/// Results from a single reminder pass.
+#[derive(Debug, Default)]
+pub struct ReminderRunResult {
+    /// Reminders sent this pass.
+    pub sent: u64,
+    /// Reminders that failed to send (retried on the next pass).
+    pub failed: u64,
+}
+
+/// Starts the access review reminder worker as a background task.
+pub async fn start_access_review_reminders_worker(
+    notifier: Arc<EmailNotifier>,
+    db: Arc<DbPool>,
+    config: AccessReviewReminderNotifications,
+    shutdown: CancellationToken,
+) {
+    if !config.enabled {
+        tracing::info!("Access review reminders disabled by configuration");
+        return;
+    }
+
+    tracing::info!(
+        interval_days = config.interval_days,
+        inactive_days = config.inactive_days,
+        "Starting access review reminder worker"
+    );
+
+    let service = AccessReviewService::new(db.clone());
+
+    loop {
+        // Sleep first so we don't race the rest of startup.
+        tokio::select! {
+            _ = shutdown.cancelled() => return,
+            _ = tokio::time::sleep(CHECK_INTERVAL) => {}
+        }
+
+        // Only one replica may send per tick, otherwise every replica
+        // would mail the same reminder.
+        let _guard = match leader_lock::try_acquire(&db, keys::ACCESS_REVIEW_REMINDERS).await {
+            LeadershipOutcome::Leader(g) => Some(g),
+            LeadershipOutcome::NotLeader => {
+                tracing::trace!("access_review_reminders: not leader this tick, skipping");
+                continue;
+            }
+            LeadershipOutcome::NoCoordination => None,
+        };
+
+        match run_reminders(&notifier, &service, &db, &config, Utc::now()).await {
+            Ok(result) if result.sent > 0 || result.failed > 0 => {
+                tracing::info!(
+                    sent = result.sent,
+                    failed = result.failed,
+                    "Access review reminder pass complete"
+                );
+            }
+            Ok(_) => {
+                tracing::debug!("Access review reminder pass complete, nothing due");
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Error sending access review reminders");
+            }
+        }
+    }
+}
+
+/// Run one pass: remind every organization that is due and has stale access.
+async fn run_reminders(
+    notifier: &EmailNotifier,
+    service: &AccessReviewService,
+    db: &Arc<DbPool>,
+    config: &AccessReviewReminderNotifications,
+    now: DateTime<Utc>,
+) -> Result<ReminderRunResult, NotifyError> {
+    let mut result = ReminderRunResult::default();
+    let due_before = now - Duration::days(config.interval_days as i64);
+
+    let mut params = ListParams {
+        limit: Some(500),
+        ..Default::default()
+    };
+    loop {
+        let page = db.organizations().list(params.clone()).await?;
+        for org in &page.items {
+            if !is_due(db, org, due_before).await? {
+                continue;
+            }
+
+            let stale = service
+                .get_stale_access(config.inactive_days as i64, Some(org.id), 1000)
+                .await?;
+            if stale.summary.stale_users_count == 0
+                && stale.summary.never_active_users_count == 0
+                && stale.summary.stale_api_keys_count == 0
+            {
+                continue;
+            }
+
+            match send_reminder(notifier, db, config, org, &stale).await {
+                Ok(()) => result.sent += 1,
+                Err(e) => {
+                    result.failed += 1;
+                    tracing::warn!(
+                        org = %org.slug,
+                        error = %e,
+                        "Failed to send access review reminder"
+                    );
+                }
+            }
+        }
+        match page.cursors.next {
+            Some(next) if page.has_more => params.cursor = Some(next),
+            _ => break,
+        }
+    }
+
+    Ok(result)
+}
+
+/// Whether the organization's last sent reminder is older than `due_before`.
+async fn is_due(
+    db: &Arc<DbPool>,
+    org: &Organization,
+    due_before: DateTime<Utc>,
+) -> Result<bool, NotifyError> {
+    let last = db
+        .email_log()
+        .list(
+            EmailLogFilter {
+                org_id: Some(org.id),
+                kind: Some(EmailKind::AccessReviewReminder),
+                status: Some(EmailSendStatus::Sent),
+            },
+            ListParams {
+                limit: Some(1),
+                ..Default::default()
+            },
+        )
+        .await?;
+    Ok(last
+        .items
+        .first()
+        .is_none_or(|entry| entry.created_at < due_before))
+}
+
+async fn send_reminder(
+    notifier: &EmailNotifier,
+    db: &Arc<DbPool>,
+    config: &AccessReviewReminderNotifications,
+    org: &Organization,
+    stale: &StaleAccessResponse,
+) -> Result<(), NotifyError> {
+    let to = db
+        .users()
+        .list_org_member_emails(org.id, &config.roles)
+        .await?;
+
+    notifier
+        .send(Notification {
+            kind: EmailKind::AccessReviewReminder,
+            org: Some(org),
+            to,
+            vars: vec![
+                ("org_name", org.name.clone()),
+                ("inactive_days", config.inactive_days.to_string()),
+                ("stale_users", stale.summary.stale_users_count.to_string()),
+                (
+                    "never_active_users",
+                    stale.summary.never_active_users_count.to_string(),
+                ),
+                (
+                    "stale_api_keys",
+                    stale.summary.stale_api_keys_count.to_string(),
+                ),
+                ("details", details(stale)),
+            ],
+        })
+        .await
+}
+
+/// Plain-text listing of the stale entries, capped at [`MAX_LISTED`] each.
+fn details(stale: &StaleAccessResponse) -> String {
+    let mut out = String::new();
+
+    let users = stale.stale_users.iter().map(|u| {
+        format!(
+            "{} ({} days inactive)",
+            u.email
+                .as_deref()
+                .or(u.name.as_deref())
+                .unwrap_or(&u.external_id),
+            u.days_inactive
+        )
+    });
+    write_section(&mut out, "Inactive users", users);
+
+    let never_active = stale.never_active_users.iter().map(|u| {
+        format!(
+            "{} (created {} days ago)",
+            u.email
+                .as_deref()
+                .or(u.name.as_deref())
+                .unwrap_or(&u.external_id),
+            u.days_since_creation
+        )
+    });
+    write_section(&mut out, "Users who never signed in", never_active);
+
+    let keys = stale.stale_api_keys.iter().map(|k| {
+        if k.never_used {
+            format!("{} ({}, never used)", k.name, k.key_prefix)
+        } else {
+            format!(
+                "{} ({}, {} days unused)",
+                k.name, k.key_prefix, k.days_inactive
+            )
+        }
+    });
+    write_section(&mut out, "Unused API keys", keys);
+
+    out
+}
+
+fn write_section(out: &mut String, title: &str, lines: impl ExactSizeIterator<Item = String>) {
+    let total = lines.len();
+    if total == 0 {
+        return;
+    }
+    let _ = writeln!(out, "{title}:");
+    for line in lines.take(MAX_LISTED) {
+        let _ = writeln!(out, "  - {line}");
+    }
+    if total > MAX_LISTED {
+        let _ = writeln!(out, "  ... and {} more", total - MAX_LISTED);
+    }
+    out.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_section_caps_lines() {
+        let mut out = String::new();
+        write_section(&mut out, "Empty", Vec::<String>::new().into_iter());
+        assert!(out.is_empty());
+
+        let lines = (0..MAX_LISTED + 3).map(|i| format!("key-{i}"));
+        write_section(&mut out, "Unused API keys", lines);
+        assert!(out.starts_with("Unused API keys:\n  - key-0\n"));
+        assert!(out.contains(&format!("  - key-{}\n", MAX_LISTED - 1)));
+        assert!(!out.contains(&format!("key-{}\n", MAX_LISTED)));
+        assert!(out.ends_with("  ... and 3 more\n\n"));
+    }
+}
@@ -0,0 +1,227 @@
+//! Usage anomaly detection worker.
+//!
+//! On every tick the worker compares each API key's and organization's
+//! usage in the last `window_hours` with the `baseline_days` before it (see
+//! [`detect`]) and records new findings in `usage_anomalies`. A finding is
+//! only recorded once per `cooldown_secs`, so an anomaly that lasts several
+//! ticks doesn't flood the findings list or the event bus.
+//!
+//! With `auto_rate_limit`, API keys with a finding of a configured kind get
+//! a requests-per-minute cap in the cache, which the rate limit middleware
+//! applies until it expires or the finding is acknowledged.
+
+use std::{sync::Arc, time::Instant};
+
+use chrono::{DateTime, Duration, Utc};
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    cache::{Cache, CacheExt, CacheKeys},
+    config::AnomalyDetectionConfig,
+    db::{DbPool, DbResult},
+    events::{EventBus, ServerEvent},
+    jobs::leader_lock::{self, LeadershipOutcome, keys},
+    models::CreateUsageAnomaly,
+    observability::metrics,
+    services::usage_anomalies::{UsageSnapshot, detect},
+};
+
+/// Results from a single detection pass.
+#[derive(Debug, Default)]
+pub struct AnomalyDetectionResult {
+    /// Findings recorded this pass.
+    pub recorded: u64,
+    /// Findings skipped because they were recorded within the cooldown.
+    pub suppressed: u64,
+    /// API keys rate limited this pass.
+    pub rate_limited: u64,
+    /// Duration of the pass in milliseconds.
+    pub duration_ms: u64,
+}
+
+/// Starts the anomaly detection worker as a background task.
+pub async fn start_anomaly_detection_worker(
+    db: Arc<DbPool>,
+    cache: Option<Arc<dyn Cache>>,
+    event_bus: Arc<EventBus>,
+    config: AnomalyDetectionConfig,
+    shutdown: CancellationToken,
+) {
+    if !config.enabled {
+        tracing::info!("Anomaly detection worker disabled by configuration");
+        return;
+    }
+    if config.auto_rate_limit.is_some() && cache.is_none() {
+        tracing::warn!(
+            "[features.anomaly_detection.auto_rate_limit] requires a cache; \
+             findings will be recorded without rate limiting"
+        );
+    }
+
+    tracing::info!(
+        interval_secs = config.interval_secs,
+        window_hours = config.window_hours,
+        baseline_days = config.baseline_days,
+        "Starting anomaly detection worker"
+    );
+
+    let interval = config.interval();
+
+    loop {
+        if shutdown.is_cancelled() {
+            tracing::info!("Anomaly detection worker received shutdown signal");
+            return;
+        }
+        // Replicas would otherwise race to record the same findings
+        let _guard = match leader_lock::try_acquire(&db, keys::ANOMALY_DETECTION).await {
+            LeadershipOutcome::Leader(g) => Some(g),
+            LeadershipOutcome::NotLeader => {
+                tracing::trace!("anomaly_detection: not leader this tick, skipping");
+                tokio::select! {
+                    _ = shutdown.cancelled() => return,
+                    _ = tokio::time::sleep(interval) => {}
+                }
+                continue;
+            }
+            LeadershipOutcome::NoCoordination => None,
+        };
+
+        match run_anomaly_detection(&db, cache.as_ref(), &event_bus, &config, Utc::now()).await {
+            Ok(result) if result.recorded > 0 => {
+                tracing::info!(
+                    recorded = result.recorded,
+                    suppressed = result.suppressed,
+                    rate_limited = result.rate_limited,
+                    duration_ms = result.duration_ms,
+                    "Anomaly detection pass complete"
+                );
+            }
+            Ok(result) => {
+                tracing::debug!(
+                    suppressed = result.suppressed,
+                    duration_ms = result.duration_ms,
+                    "Anomaly detection pass complete, no new findings"
+                );
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Error running anomaly detection");
+            }
+        }
+
+        tokio::select! {
+            _ = shutdown.cancelled() => return,
+            _ = tokio::time::sleep(interval) => {}
+        }
+    }
+}
+
+/// Run one detection pass over the window ending at `now`.
+async fn run_anomaly_detection(
+    db: &Arc<DbPool>,
+    cache: Option<&Arc<dyn Cache>>,
+    event_bus: &EventBus,
+    config: &AnomalyDetectionConfig,
+    now: DateTime<Utc>,
+) -> DbResult<AnomalyDetectionResult> {
+    let start = Instant::now();
+    let mut result = AnomalyDetectionResult::default();
+
+    let window_start = now - Duration::hours(i64::from(config.window_hours));
+    let baseline_start = window_start - Duration::days(i64::from(config.baseline_days));
+    let usage = db.usage();
+    let snapshot = UsageSnapshot {
+        baseline_spend: usage.get_hourly_spend(baseline_start, window_start).await?,
+        window_spend: usage.get_hourly_spend(window_start, now).await?,
+        baseline_mix: usage.get_usage_mix(baseline_start, window_start).await?,
+        window_mix: usage.get_usage_mix(window_start, now).await?,
+    };
+
+    let cooldown_start = now - Duration::seconds(config.cooldown_secs as i64);
+    for mut finding in detect(config, &snapshot, window_start, now) {
+        if db
+            .usage_anomalies()
+            .exists_since(&finding.fingerprint, cooldown_start)
+            .await?
+        {
+            result.suppressed += 1;
+            continue;
+        }
+
+        if let Some(cache) = cache {
+            finding.rate_limited_until = apply_rate_limit(cache, config, &finding, now).await;
+        }
+        let rate_limited = finding.rate_limited_until.is_some();
+        if rate_limited {
+            result.rate_limited += 1;
+        }
+
+        let anomaly = db.usage_anomalies().create(finding).await?;
+        result.recorded += 1;
+
+        let subject = if anomaly.api_key_id.is_some() {
+            "api_key"
+        } else {
+            "org"
+        };
+        metrics::record_usage_anomaly(anomaly.kind.as_str(), subject, rate_limited);
+        tracing::warn!(
+            anomaly_id = %anomaly.id,
+            kind = anomaly.kind.as_str(),
+            org_id = ?anomaly.org_id,
+            api_key_id = ?anomaly.api_key_id,
+            score = anomaly.score,
+            rate_limited,
+            "{}",
+            anomaly.summary
+        );
+        event_bus.publish(ServerEvent::UsageAnomalyDetected {
+            anomaly_id: anomaly.id,
+            timestamp: anomaly.created_at,
+            kind: anomaly.kind,
+            org_id: anomaly.org_id,
+            api_key_id: anomaly.api_key_id,
+            detail: anomaly.detail,
+            summary: anomaly.summary,
+            score: anomaly.score,
+            rate_limited_until: anomaly.rate_limited_until,
+        });
+    }
+
+    result.duration_ms = start.elapsed().as_millis() as u64;
+    Ok(result)
+}
+
+/// Cap the finding's API key if its kind is configured for rate limiting,
+/// returning when the cap lifts.
+async fn apply_rate_limit(
+    cache: &Arc<dyn Cache>,
+    config: &AnomalyDetectionConfig,
+    finding: &CreateUsageAnomaly,
+    now: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    let limit = config.auto_rate_limit.as_ref()?;
+    let api_key_id = finding.api_key_id?;
+    if !limit.kinds.contains(&finding.kind) {
+        return None;
+    }
+
+    let ttl = std::time::Duration::from_secs(limit.duration_secs);
+    match cache
+        .set_json(
+            &CacheKeys::anomaly_rate_limit(api_key_id),
+            &limit.requests_per_minute,
+            ttl,
+        )
+        .await
+    {
+        Ok(()) => Some(now + Duration::seconds(limit.duration_secs as i64)),
+        Err(e) => {
+            tracing::warn!(
+                error = %e,
+                %api_key_id,
+                "Failed to apply anomaly rate limit"
+            );
+            None
+        }
+    }
+}
@@ -0,0 +1,152 @@
+//! Exchanges circuit breaker opens between replicas through the cache.
+//!
+//! Breakers configured with `mode = "shared"` still trip on their own node's
+//! failures. Every tick this worker writes any local open that is newer than
+//! the one in the cache, and opens the local breaker for any newer open found
+//! there. The cache entry expires when the open timeout does, so recovery is
+//! still probed by whichever node moves to half-open first.
+
+use std::{sync::Arc, time::Duration};
+
+use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    cache::{Cache, CacheExt, CacheKeys},
+    observability::metrics,
+    providers::{CircuitBreakerRegistry, SharedOpenState, circuit_breaker::CircuitBreaker},
+};
+
+/// How often shared state is exchanged. This bounds how long other replicas
+/// keep sending traffic after one of them trips.
+const SYNC_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Spawnable entry point. Exits when `shutdown` is cancelled.
+pub async fn start_circuit_breaker_sync_worker(
+    cache: Arc<dyn Cache>,
+    circuit_breakers: CircuitBreakerRegistry,
+    shutdown: CancellationToken,
+) {
+    tracing::info!("Starting shared circuit breaker sync");
+    loop {
+        tokio::select! {
+            _ = shutdown.cancelled() => {
+                tracing::info!("Circuit breaker sync received shutdown signal");
+                return;
+            }
+            _ = sleep(SYNC_INTERVAL) => {}
+        }
+
+        for (provider, breaker) in circuit_breakers.shared() {
+            if let Err(e) = sync_breaker(cache.as_ref(), &provider, &breaker).await {
+                tracing::warn!(provider = %provider, error = %e, "Circuit breaker sync failed");
+                metrics::record_circuit_breaker_sync(&provider, "error");
+            }
+        }
+    }
+}
+
+/// Sync one breaker with the cache.
+async fn sync_breaker(
+    cache: &dyn Cache,
+    provider: &str,
+    breaker: &CircuitBreaker,
+) -> Result<(), String> {
+    let key = CacheKeys::circuit_breaker(provider);
+    let shared: Option<SharedOpenState> = cache
+        .get_json(&key)
+        .await
+        .map_err(|e| format!("failed to read shared state: {e}"))?;
+    let shared_until = shared.map_or(0, |s| s.open_until_millis());
+
+    if let Some(local) = breaker.open_state()
+        && local.open_until_millis() > shared_until
+    {
+        let now = chrono::Utc::now().timestamp_millis().max(0) as u64;
+        let remaining = local.open_until_millis().saturating_sub(now);
+        if remaining > 0 {
+            cache
+                .set_json(&key, &local, Duration::from_millis(remaining))
+                .await
+                .map_err(|e| format!("failed to publish open: {e}"))?;
+            tracing::debug!(provider, "Published circuit breaker open");
+            metrics::record_circuit_breaker_sync(provider, "published");
+        }
+        return Ok(());
+    }
+
+    if let Some(shared) = shared
+        && breaker.apply_shared_open(shared)
+    {
+        metrics::record_circuit_breaker_sync(provider, "applied");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cache::MemoryCache,
+        config::{CircuitBreakerConfig, CircuitBreakerMode},
+        providers::circuit_breaker::CircuitState,
+    };
+
+    fn shared_config() -> CircuitBreakerConfig {
+        CircuitBreakerConfig {
+            enabled: true,
+            failure_threshold: 2,
+            open_timeout_secs: 30,
+            mode: CircuitBreakerMode::Shared,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_open_propagates_between_nodes() {
+        let cache = MemoryCache::new(&Default::default());
+        let node_a = CircuitBreakerRegistry::new();
+        let node_b = CircuitBreakerRegistry::new();
+        let a = node_a.get_or_create("openai", &shared_config()).unwrap();
+        let b = node_b.get_or_create("openai", &shared_config()).unwrap();
+
+        // Nothing to exchange while both are closed
+        sync_breaker(&cache, "openai", &a).await.unwrap();
+        sync_breaker(&cache, "openai", &b).await.unwrap();
+        assert_eq!(b.state(), CircuitState::Closed);
+
+        a.record_failure();
+        a.record_failure();
+        assert_eq!(a.state(), CircuitState::Open);
+
+        sync_breaker(&cache, "openai", &a).await.unwrap();
+        sync_breaker(&cache, "openai", &b).await.unwrap();
+        assert_eq!(b.state(), CircuitState::Open);
+        assert_eq!(b.open_state(), a.open_state());
+
+        // Applying the open doesn't make node B publish it back as its own
+        sync_breaker(&cache, "openai", &b).await.unwrap();
+        let cached: Option<SharedOpenState> = cache
+            .get_json(&CacheKeys::circuit_breaker("openai"))
+            .await
+            .unwrap();
+        assert_eq!(cached, a.open_state());
+    }
+
+    #[test]
+    fn test_local_breakers_not_shared() {
+        let registry = CircuitBreakerRegistry::new();
+        registry.get_or_create(
+            "local",
+            &CircuitBreakerConfig {
+                enabled: true,
+                ..Default::default()
+            },
+        );
+        registry.get_or_create("shared", &shared_config());
+
+        let shared = registry.shared();
+        assert_eq!(shared.len(), 1);
+        assert_eq!(shared[0].0, "shared");
+    }
+}
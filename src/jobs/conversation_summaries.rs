@@ -0,0 +1,281 @@
+//! Conversation summaries worker.
+//!
+//! On every tick the worker finds conversations whose summary is missing or
+//! has fallen `resummarize_after_messages` behind, and asks the configured
+//! model for a title and summary. Generated titles only replace placeholder
+//! titles such as "New Chat", so names chosen by users are never touched.
+//!
+//! Organizations opt in or out (and may pick a different model) through
+//! their `conversation_summaries` setting; personal conversations follow
+//! `default_enabled`. Progress is stored on the conversation itself, so the
+//! worker is restart-safe and conversations that fail are retried next tick.
+
+use std::{sync::Arc, time::Instant};
+
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use crate::{
+    config::ConversationSummariesConfig,
+    db::{DbError, DbPool, DbResult, ListParams},
+    jobs::leader_lock::{self, LeadershipOutcome, keys},
+    models::{Conversation, ConversationSummarySettings, SetConversationSummary},
+    observability::metrics,
+    services::{ConversationService, ConversationSummarizer},
+};
+
+/// Results from a single summarization pass.
+#[derive(Debug, Default)]
+pub struct ConversationSummaryResult {
+    /// Conversations summarized this pass.
+    pub summarized: u64,
+    /// Conversations whose generation failed (retried next pass).
+    pub failed: u64,
+    /// Duration of the pass in milliseconds.
+    pub duration_ms: u64,
+}
+
+/// Starts the conversation summaries worker as a background task.
+pub async fn start_conversation_summaries_worker(
+    summarizer: ConversationSummarizer,
+    conversations: ConversationService,
+    db: Arc<DbPool>,
+    config: ConversationSummariesConfig,
+    shutdown: CancellationToken,
+) {
+    if !config.enabled {
+        tracing::info!("Conversation summaries worker disabled by configuration");
+        return;
+    }
+
+    tracing::info!(
+        interval_secs = config.interval_secs,
+        provider = %config.provider,
+        model = %config.model,
+        "Starting conversation summaries worker"
+    );
+
+    let interval = config.interval();
+
+    loop {
+        if shutdown.is_cancelled() {
+            tracing::info!("Conversation summaries worker received shutdown signal");
+            return;
+        }
+        // Replicas would otherwise pay for the same generations twice.
+        let _guard = match leader_lock::try_acquire(&db, keys::CONVERSATION_SUMMARIES).await {
+            LeadershipOutcome::Leader(g) => Some(g),
+            LeadershipOutcome::NotLeader => {
+                tracing::trace!("conversation_summaries: not leader this tick, skipping");
+                tokio::select! {
+                    _ = shutdown.cancelled() => return,
+                    _ = tokio::time::sleep(interval) => {}
+                }
+                continue;
+            }
+            LeadershipOutcome::NoCoordination => None,
+        };
+
+        match run_conversation_summaries(&summarizer, &conversations, &db, &config, &shutdown).await
+        {
+            Ok(result) if result.summarized > 0 || result.failed > 0 => {
+                tracing::info!(
+                    summarized = result.summarized,
+                    failed = result.failed,
+                    duration_ms = result.duration_ms,
+                    "Conversation summaries pass complete"
+                );
+            }
+            Ok(_) => {
+                tracing::debug!("Conversation summaries pass complete, nothing to summarize");
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Error running conversation summaries");
+            }
+        }
+
+        tokio::select! {
+            _ = shutdown.cancelled() => return,
+            _ = tokio::time::sleep(interval) => {}
+        }
+    }
+}
+
+/// Run one pass, summarizing at most `batch_size` conversations.
+async fn run_conversation_summaries(
+    summarizer: &ConversationSummarizer,
+    conversations: &ConversationService,
+    db: &Arc<DbPool>,
+    config: &ConversationSummariesConfig,
+    shutdown: &CancellationToken,
+) -> DbResult<ConversationSummaryResult> {
+    let start = Instant::now();
+    let mut result = ConversationSummaryResult::default();
+    let mut budget = i64::from(config.batch_size);
+
+    for (org_id, model) in summary_targets(db, config).await? {
+        if budget <= 0 || shutdown.is_cancelled() {
+            break;
+        }
+        let candidates = conversations
+            .list_needing_summary(
+                org_id,
+                config.min_messages,
+                config.resummarize_after_messages,
+                budget,
+            )
+            .await?;
+        budget -= candidates.len() as i64;
+
+        for conversation in candidates {
+            if shutdown.is_cancelled() {
+                break;
+            }
+            if summarize_one(summarizer, conversations, config, &conversation, &model).await? {
+                result.summarized += 1;
+                metrics::record_conversation_summary("succeeded");
+            } else {
+                result.failed += 1;
+                metrics::record_conversation_summary("failed");
+            }
+        }
+    }
+
+    result.duration_ms = start.elapsed().as_millis() as u64;
+    Ok(result)
+}
+
+/// Generate and store one summary. Returns `false` when generation failed.
+async fn summarize_one(
+    summarizer: &ConversationSummarizer,
+    conversations: &ConversationService,
+    config: &ConversationSummariesConfig,
+    conversation: &Conversation,
+    model: &str,
+) -> DbResult<bool> {
+    let generated = match summarizer.summarize(conversation, model).await {
+        Ok(generated) => generated,
+        Err(e) => {
+            tracing::warn!(
+                conversation_id = %conversation.id,
+                model = %model,
+                error = %e,
+                "Conversation summary generation failed"
+            );
+            return Ok(false);
+        }
+    };
+
+    let title = (config.is_placeholder_title(&conversation.title) && !generated.title.is_empty())
+        .then_some(generated.title);
+    let input = SetConversationSummary {
+        summary: generated.summary,
+        message_count: conversation.messages.len() as i32,
+        title,
+        expected_title: conversation.title.clone(),
+    };
+
+    match conversations.set_summary(conversation.id, input).await {
+        // Deleted while the summary was being generated
+        Ok(()) | Err(DbError::NotFound) => Ok(true),
+        Err(e) => Err(e),
+    }
+}
+
+/// Owners to summarize this pass and the model for each: `None` for personal
+/// conversations, then every organization that hasn't opted out.
+async fn summary_targets(
+    db: &Arc<DbPool>,
+    config: &ConversationSummariesConfig,
+) -> DbResult<Vec<(Option<Uuid>, String)>> {
+    let mut targets = Vec::new();
+    if let Some(model) = summary_model(None, config) {
+        targets.push((None, model));
+    }
+
+    let mut params = ListParams {
+        limit: Some(500),
+        ..Default::default()
+    };
+    loop {
+        let page = db.organizations().list(params.clone()).await?;
+        for org in page.items {
+            if let Some(model) = summary_model(org.conversation_summaries.as_ref(), config) {
+                targets.push((Some(org.id), model));
+            }
+        }
+        match page.cursors.next {
+            Some(next) if page.has_more => params.cursor = Some(next),
+            _ => break,
+        }
+    }
+    Ok(targets)
+}
+
+/// The model to summarize with, or `None` when summaries are disabled for
+/// the owner. Owners without settings follow `default_enabled`.
+fn summary_model(
+    settings: Option<&ConversationSummarySettings>,
+    config: &ConversationSummariesConfig,
+) -> Option<String> {
+    match settings {
+        None if config.default_enabled => Some(config.model.clone()),
+        None => None,
+        Some(s) if s.enabled => Some(s.model.clone().unwrap_or_else(|| config.model.clone())),
+        Some(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(default_enabled: bool) -> ConversationSummariesConfig {
+        ConversationSummariesConfig {
+            enabled: true,
+            provider: "openai".into(),
+            model: "gpt-4o-mini".into(),
+            default_enabled,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_summary_model_defaults() {
+        assert_eq!(
+            summary_model(None, &config(true)).as_deref(),
+            Some("gpt-4o-mini")
+        );
+        assert_eq!(summary_model(None, &config(false)), None);
+    }
+
+    #[test]
+    fn test_summary_model_org_override() {
+        let settings = ConversationSummarySettings {
+            enabled: true,
+            model: Some("claude-haiku".into()),
+        };
+        assert_eq!(
+            summary_model(Some(&settings), &config(false)).as_deref(),
+            Some("claude-haiku")
+        );
+
+        let settings = ConversationSummarySettings {
+            enabled: true,
+            model: None,
+        };
+        assert_eq!(
+            summary_model(Some(&settings), &config(false)).as_deref(),
+            Some("gpt-4o-mini")
+        );
+    }
+
+    #[test]
+    fn test_summary_model_org_opt_out() {
+        let settings = ConversationSummarySettings {
+            enabled: false,
+            model: Some("claude-haiku".into()),
+        };
+        assert_eq!(summary_model(Some(&settings), &config(true)), None);
+    }
+}
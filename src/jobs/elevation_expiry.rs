@@ -0,0 +1,66 @@
+//! Role elevation expiry worker.
+//!
+//! Every `[features.elevations] interval_secs`, active role elevations whose
+//! `expires_at` has passed are marked expired and the member's previous
+//! organization role is restored. Each one is written to the audit log as
+//! `elevation.expire`.
+
+use std::{sync::Arc, time::Duration};
+
+use chrono::Utc;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    config::ElevationsConfig,
+    db::DbPool,
+    jobs::leader_lock::{self, LeadershipOutcome, keys},
+    services::ElevationService,
+};
+
+/// Starts the role elevation expiry worker as a background task.
+pub async fn start_elevation_expiry_worker(
+    db: Arc<DbPool>,
+    config: ElevationsConfig,
+    shutdown: CancellationToken,
+) {
+    if !config.enabled {
+        tracing::info!("Role elevations disabled by configuration");
+        return;
+    }
+
+    tracing::info!(
+        interval_secs = config.interval_secs,
+        "Starting role elevation expiry worker"
+    );
+
+    let service = ElevationService::new(db.clone());
+    let interval = Duration::from_secs(config.interval_secs);
+
+    loop {
+        tokio::select! {
+            _ = shutdown.cancelled() => return,
+            _ = tokio::time::sleep(interval) => {}
+        }
+
+        // Elevations are claimed row by row, so running on every replica
+        // would be safe; the lock just avoids the redundant queries.
+        let _guard = match leader_lock::try_acquire(&db, keys::ELEVATION_EXPIRY).await {
+            LeadershipOutcome::Leader(g) => Some(g),
+            LeadershipOutcome::NotLeader => {
+                tracing::trace!("elevation_expiry: not leader this tick, skipping");
+                continue;
+            }
+            LeadershipOutcome::NoCoordination => None,
+        };
+
+        match service.expire_due(Utc::now()).await {
+            Ok(0) => {}
+            Ok(expired) => {
+                tracing::info!(expired, "Reverted expired role elevations");
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Error reverting expired role elevations");
+            }
+        }
+    }
+}
@@ -0,0 +1,243 @@
+//! Satellite side of multi-gateway federation.
+//!
+//! Every `interval_secs` the worker reads the last `lookback_days` of daily
+//! per-model usage from the local database, snapshots provider health and
+//! circuit breaker state, and POSTs the result to the hub configured in
+//! `[federation.satellite]`. Days are always sent whole, so the hub can
+//! overwrite what it has and a missed report is made good by the next one.
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use chrono::{Duration, NaiveDate, Utc};
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    config::FederationSatelliteConfig,
+    db::{DateRange, DbPool},
+    jobs::{
+        ProviderHealthState, ProviderHealthStateRegistry,
+        leader_lock::{self, LeadershipOutcome, keys},
+    },
+    models::{FederatedProviderHealth, FederatedUsage, FederationReport},
+    providers::{CircuitBreakerRegistry, CircuitBreakerStatus, registry::endpoint_key},
+};
+
+/// Starts the federation reporter as a background task.
+pub async fn start_federation_reporter_worker(
+    db: Arc<DbPool>,
+    http_client: reqwest::Client,
+    config: FederationSatelliteConfig,
+    provider_health: ProviderHealthStateRegistry,
+    circuit_breakers: CircuitBreakerRegistry,
+    shutdown: CancellationToken,
+) {
+    tracing::info!(
+        hub = %config.hub_url,
+        gateway_id = %config.gateway_id,
+        interval_secs = config.interval_secs,
+        "Starting federation reporter"
+    );
+
+    let interval = config.interval();
+
+    loop {
+        if shutdown.is_cancelled() {
+            tracing::info!("Federation reporter received shutdown signal");
+            return;
+        }
+        // Usage lives in the shared database, so one replica reporting per
+        // tick is enough.
+        let guard = match leader_lock::try_acquire(&db, keys::FEDERATION_REPORTER).await {
+            LeadershipOutcome::Leader(g) => Some(g),
+            LeadershipOutcome::NotLeader => {
+                tracing::trace!("federation_reporter: not leader this tick, skipping");
+                tokio::select! {
+                    _ = shutdown.cancelled() => return,
+                    _ = tokio::time::sleep(interval) => {}
+                }
+                continue;
+            }
+            LeadershipOutcome::NoCoordination => None,
+        };
+
+        match send_report(
+            &db,
+            &http_client,
+            &config,
+            &provider_health,
+            &circuit_breakers,
+        )
+        .await
+        {
+            Ok(usage_rows) => {
+                tracing::debug!(usage_rows, "Sent federation report");
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, hub = %config.hub_url, "Failed to send federation report");
+            }
+        }
+        drop(guard);
+
+        tokio::select! {
+            _ = shutdown.cancelled() => return,
+            _ = tokio::time::sleep(interval) => {}
+        }
+    }
+}
+
+/// Build and deliver one report. Returns the number of usage rows sent.
+async fn send_report(
+    db: &Arc<DbPool>,
+    http_client: &reqwest::Client,
+    config: &FederationSatelliteConfig,
+    provider_health: &ProviderHealthStateRegistry,
+    circuit_breakers: &CircuitBreakerRegistry,
+) -> Result<usize, String> {
+    let now = Utc::now();
+    let usage = db
+        .usage()
+        .get_daily_model_usage_global(lookback_range(now.date_naive(), config.lookback_days))
+        .await
+        .map_err(|e| format!("failed to read usage: {e}"))?;
+
+    let report = FederationReport {
+        gateway_id: config.gateway_id.clone(),
+        region: config.region.clone(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        generated_at: now,
+        usage: usage.into_iter().map(FederatedUsage::from).collect(),
+        providers: merge_provider_health(provider_health.get_all(), circuit_breakers.status()),
+    };
+    let usage_rows = report.usage.len();
+
+    let response = http_client
+        .post(config.reports_url())
+        .bearer_auth(&config.token)
+        .timeout(std::time::Duration::from_secs(config.timeout_secs))
+        .json(&report)
+        .send()
+        .await
+        .map_err(|e| format!("request failed: {e}"))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!(
+            "hub returned {status}: {}",
+            body.chars().take(200).collect::<String>()
+        ));
+    }
+    Ok(usage_rows)
+}
+
+/// Inclusive range covering `lookback_days` days ending today.
+fn lookback_range(today: NaiveDate, lookback_days: u32) -> DateRange {
+    DateRange {
+        start: today - Duration::days(i64::from(lookback_days.saturating_sub(1))),
+        end: today,
+    }
+}
+
+/// Combine health-check results and circuit breaker state into one entry per
+/// provider, or per `<provider>/<endpoint>` for providers with several
+/// endpoints. Providers without health checks still appear if they have a
+/// circuit breaker, and vice versa.
+fn merge_provider_health(
+    health: Vec<ProviderHealthState>,
+    breakers: Vec<CircuitBreakerStatus>,
+) -> Vec<FederatedProviderHealth> {
+    let mut merged: BTreeMap<String, FederatedProviderHealth> = BTreeMap::new();
+    for state in health {
+        merged.insert(
+            state.provider.clone(),
+            FederatedProviderHealth {
+                provider: state.provider,
+                status: state.status,
+                latency_ms: Some(state.latency_ms),
+                circuit_state: None,
+            },
+        );
+    }
+    for breaker in breakers {
+        let name = match &breaker.endpoint {
+            Some(endpoint) => endpoint_key(&breaker.provider, endpoint),
+            None => breaker.provider,
+        };
+        merged
+            .entry(name.clone())
+            .or_insert_with(|| FederatedProviderHealth {
+                provider: name,
+                status: Default::default(),
+                latency_ms: None,
+                circuit_state: None,
+            })
+            .circuit_state = Some(breaker.state);
+    }
+    merged.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::{circuit_breaker::CircuitState, health_check::HealthStatus};
+
+    #[test]
+    fn test_lookback_range() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+        let range = lookback_range(today, 2);
+        assert_eq!(range.start, NaiveDate::from_ymd_opt(2025, 3, 9).unwrap());
+        assert_eq!(range.end, today);
+
+        let range = lookback_range(today, 1);
+        assert_eq!(range.start, today);
+    }
+
+    #[test]
+    fn test_merge_provider_health() {
+        let health = vec![ProviderHealthState {
+            provider: "openai".to_string(),
+            status: HealthStatus::Healthy,
+            latency_ms: 120,
+            error: None,
+            status_code: Some(200),
+            last_check: Utc::now(),
+            consecutive_failures: 0,
+            consecutive_successes: 3,
+        }];
+        let breakers = vec![
+            CircuitBreakerStatus {
+                provider: "openai".to_string(),
+                state: CircuitState::Closed,
+                failure_count: 0,
+                endpoint: None,
+                latency_ms: None,
+            },
+            CircuitBreakerStatus {
+                provider: "anthropic".to_string(),
+                state: CircuitState::Open,
+                failure_count: 5,
+                endpoint: None,
+                latency_ms: None,
+            },
+        ];
+
+        let merged = merge_provider_health(health, breakers);
+        assert_eq!(
+            merged,
+            vec![
+                FederatedProviderHealth {
+                    provider: "anthropic".to_string(),
+                    status: HealthStatus::Unknown,
+                    latency_ms: None,
+                    circuit_state: Some(CircuitState::Open),
+                },
+                FederatedProviderHealth {
+                    provider: "openai".to_string(),
+                    status: HealthStatus::Healthy,
+                    latency_ms: Some(120),
+                    circuit_state: Some(CircuitState::Closed),
+                },
+            ]
+        );
+    }
+}
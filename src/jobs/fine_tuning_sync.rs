@@ -0,0 +1,70 @@
+//! Polls providers for fine-tuning jobs that haven't finished.
+//!
+//! Each pass refreshes every incomplete job in `fine_tuning_jobs`, so jobs
+//! complete (usage recorded, model registered) even if no client reads them
+//! again. Runs whenever a database is configured; with no incomplete jobs a
+//! pass is a single query.
+
+use std::time::Duration;
+
+use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    AppState,
+    jobs::leader_lock::{self, LeadershipOutcome, keys},
+    services::fine_tuning,
+};
+
+/// Jobs refreshed per pass. Older updates go first, so a backlog is worked
+/// through over several passes.
+const BATCH_SIZE: i64 = 100;
+
+/// Spawnable entry point. Exits when `shutdown` is cancelled.
+pub async fn start_fine_tuning_sync_worker(state: AppState, shutdown: CancellationToken) {
+    let Some(db) = state.db.clone() else {
+        return;
+    };
+    let interval = Duration::from_secs(state.config.features.fine_tuning.poll_interval_secs);
+    tracing::info!(
+        interval_secs = interval.as_secs(),
+        "Starting fine-tuning job sync worker"
+    );
+
+    loop {
+        tokio::select! {
+            _ = shutdown.cancelled() => {
+                tracing::info!("Fine-tuning job sync worker received shutdown signal");
+                return;
+            }
+            _ = sleep(interval) => {}
+        }
+
+        let _guard = match leader_lock::try_acquire(&db, keys::FINE_TUNING_SYNC).await {
+            LeadershipOutcome::Leader(g) => Some(g),
+            LeadershipOutcome::NotLeader => {
+                tracing::trace!("fine_tuning_sync: not leader this tick, skipping");
+                continue;
+            }
+            LeadershipOutcome::NoCoordination => None,
+        };
+
+        let jobs = match db.fine_tuning_jobs().list_incomplete(BATCH_SIZE).await {
+            Ok(jobs) => jobs,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to list incomplete fine-tuning jobs");
+                continue;
+            }
+        };
+        for job in jobs {
+            if let Err(e) = fine_tuning::sync_job(&state, &db, &job).await {
+                tracing::warn!(
+                    error = %e,
+                    job_id = %job.public_id(),
+                    provider = %job.provider,
+                    "Failed to sync fine-tuning job"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,54 @@
+//! Background cleanup for expired idempotency keys.
+//!
+//! A claim is only replaced when the same key is used again after it
+//! expires, so keys that are never repeated, along with their stored
+//! responses, stay in `idempotency_keys` until this worker removes them.
+//! Runs whenever `[features.idempotency]` is enabled with a database.
+
+use std::{sync::Arc, time::Duration as StdDuration};
+
+use chrono::Utc;
+use tokio::time::sleep;
+
+use crate::{
+    db::DbPool,
+    jobs::leader_lock::{self, LeadershipOutcome, keys},
+};
+
+/// How often to run the cleanup pass. The DELETE uses the `expires_at`
+/// index, and stored responses can be large, so expired rows shouldn't
+/// linger much past their TTL.
+const CLEANUP_INTERVAL: StdDuration = StdDuration::from_secs(600);
+
+/// Spawnable entry point. Loops indefinitely; intended to run under
+/// `tokio::spawn`.
+pub async fn start_idempotency_cleanup_worker(db: Arc<DbPool>) {
+    tracing::info!(
+        interval_secs = CLEANUP_INTERVAL.as_secs(),
+        "Starting idempotency key cleanup worker"
+    );
+
+    loop {
+        // Sleep first so we don't race the rest of startup.
+        sleep(CLEANUP_INTERVAL).await;
+
+        let _guard = match leader_lock::try_acquire(&db, keys::IDEMPOTENCY_CLEANUP).await {
+            LeadershipOutcome::Leader(g) => Some(g),
+            LeadershipOutcome::NotLeader => {
+                tracing::trace!("idempotency_cleanup: not leader this tick, skipping");
+                continue;
+            }
+            LeadershipOutcome::NoCoordination => None,
+        };
+
+        match db.idempotency_keys().delete_expired(Utc::now()).await {
+            Ok(0) => {}
+            Ok(n) => {
+                tracing::debug!(deleted = n, "Cleaned up expired idempotency keys");
+            }
+            Err(err) => {
+                tracing::warn!(error = %err, "Idempotency key cleanup failed");
+            }
+        }
+    }
+}
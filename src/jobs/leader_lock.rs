@@ -1,10 +1,11 @@
 //! Cross-replica leader election for periodic background jobs.
 //!
 //! Without coordination every gateway replica runs every cleanup tick — that
-//! duplicates upstream calls (vector store deletes, provider health probes),
-//! emits redundant events, and wastes egress. We use Postgres'
+//! duplicates upstream calls (vector store deletes, DLQ replays), emits
+//! redundant events, and wastes egress. We use Postgres'
 //! `pg_try_advisory_lock(bigint)` (session-level) for the duration of a
-//! single tick.
+//! single tick. Failover is automatic: a replica that dies mid-tick loses its
+//! session and with it the lock, and the next replica to tick takes over.
 //!
 //! Postgres only releases session-level advisory locks when the holding
 //! session ends, so we explicitly call `pg_advisory_unlock` on Drop and only
@@ -15,22 +16,82 @@
 //!
 //! SQLite is single-process by construction, so the helper is a no-op there;
 //! every tick proceeds.
+//!
+//! Each tick that runs is recorded in `job_leaders` against this node's
+//! [`node_id`], which is what `GET /admin/v1/system/jobs` reports.
+
+use std::sync::OnceLock;
+
+use chrono::Utc;
 
 use crate::db::DbPool;
 
+/// A cluster-wide job: its name in `job_leaders` and its advisory lock key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JobKey {
+    pub name: &'static str,
+    pub lock_id: i64,
+}
+
+impl JobKey {
+    const fn new(name: &'static str, lock_id: u64) -> Self {
+        Self {
+            name,
+            lock_id: lock_id as i64,
+        }
+    }
+}
+
 /// Stable lock keys (random 64-bit constants). Don't reuse across jobs.
 ///
-/// Only cleanup-style workers — those whose work is shared global state
-/// (DB rows, external storage) — get a key here. `model_catalog_sync` and
+/// Only workers whose work is shared global state (DB rows, external
+/// storage) get a key here. `model_catalog_sync` and
 /// `provider_health_check` deliberately don't, because they fan out per-
 /// replica state (in-memory registries, circuit breakers) that every
-/// replica must compute independently.
+/// replica must compute independently. Provider probes stay per-replica
+/// too: they measure latency from each replica's own network path.
 pub mod keys {
-    pub const VECTOR_STORE_CLEANUP: i64 = 0x6861_6472_5f76_7363_u64 as i64;
-    pub const OAUTH_CODE_CLEANUP: i64 = 0x6861_6472_5f6f_6163_u64 as i64;
-    pub const RESPONSES_RETENTION: i64 = 0x6861_6472_5f72_6573_u64 as i64;
-    pub const CONTAINERS_REAPER: i64 = 0x6861_6472_5f63_7472_u64 as i64;
-    pub const CONTAINERS_CLEANUP: i64 = 0x6861_6472_5f63_636c_u64 as i64;
+    use super::JobKey;
+
+    pub const VECTOR_STORE_CLEANUP: JobKey =
+        JobKey::new("vector_store_cleanup", 0x6861_6472_5f76_7363);
+    pub const OAUTH_CODE_CLEANUP: JobKey = JobKey::new("oauth_code_cleanup", 0x6861_6472_5f6f_6163);
+    pub const RESPONSES_RETENTION: JobKey =
+        JobKey::new("responses_retention", 0x6861_6472_5f72_6573);
+    pub const CONTAINERS_REAPER: JobKey = JobKey::new("containers_reaper", 0x6861_6472_5f63_7472);
+    pub const CONTAINERS_CLEANUP: JobKey = JobKey::new("containers_cleanup", 0x6861_6472_5f63_636c);
+    pub const SCHEDULED_REPORTS: JobKey = JobKey::new("scheduled_reports", 0x6861_6472_5f73_7270);
+    pub const FEDERATION_REPORTER: JobKey =
+        JobKey::new("federation_reporter", 0x6861_6472_5f66_6472);
+    pub const VECTOR_STORE_SYNC: JobKey = JobKey::new("vector_store_sync", 0x6861_6472_5f76_7379);
+    pub const CONVERSATION_SUMMARIES: JobKey =
+        JobKey::new("conversation_summaries", 0x6861_6472_5f63_736d);
+    pub const ANOMALY_DETECTION: JobKey = JobKey::new("anomaly_detection", 0x6861_6472_5f61_6e64);
+    pub const RETENTION: JobKey = JobKey::new("retention", 0x6861_6472_5f72_746e);
+    pub const DLQ_RETRY: JobKey = JobKey::new("dlq_retry", 0x6861_6472_5f64_6c71);
+    pub const DLQ_REDRIVE: JobKey = JobKey::new("dlq_redrive", 0x6861_6472_5f64_7264);
+    pub const IDEMPOTENCY_CLEANUP: JobKey =
+        JobKey::new("idempotency_cleanup", 0x6861_6472_5f69_646d);
+    pub const FINE_TUNING_SYNC: JobKey = JobKey::new("fine_tuning_sync", 0x6861_6472_5f66_7473);
+    pub const SCHEDULED_TASKS: JobKey = JobKey::new("scheduled_tasks", 0x6861_6472_5f73_746b);
+    pub const ACCESS_REVIEW_REMINDERS: JobKey =
+        JobKey::new("access_review_reminders", 0x6861_6472_5f61_7272);
+    pub const ELEVATION_EXPIRY: JobKey = JobKey::new("elevation_expiry", 0x6861_6472_5f65_6c76);
+}
+
+/// This node's identity in `job_leaders`.
+///
+/// Taken from `HADRIAN_NODE_ID`, else `HOSTNAME` (the pod name on
+/// Kubernetes), else a random ID generated once per process.
+pub fn node_id() -> &'static str {
+    static NODE_ID: OnceLock<String> = OnceLock::new();
+    NODE_ID.get_or_init(|| {
+        ["HADRIAN_NODE_ID", "HOSTNAME"]
+            .into_iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|v| !v.trim().is_empty())
+            .unwrap_or_else(|| format!("node-{}", &uuid::Uuid::new_v4().simple().to_string()[..12]))
+    })
 }
 
 /// Outcome of a leader-election attempt.
@@ -97,10 +158,28 @@ impl Drop for LeaderGuard {
     }
 }
 
-/// Try to acquire the named advisory lock for the duration of the returned
+/// Try to acquire the job's advisory lock for the duration of the returned
 /// guard. Returns `LeadershipOutcome::NoCoordination` for SQLite so existing
 /// single-replica deployments keep behaving as before.
-pub async fn try_acquire(db: &DbPool, key: i64) -> LeadershipOutcome {
+///
+/// Unless another replica holds the lock, the run is recorded against this
+/// node in `job_leaders`.
+pub async fn try_acquire(db: &DbPool, job: JobKey) -> LeadershipOutcome {
+    let outcome = acquire(db, job.lock_id).await;
+    let leader = !matches!(outcome, LeadershipOutcome::NotLeader);
+    crate::observability::metrics::set_job_leader(job.name, leader);
+    if leader
+        && let Err(e) = db
+            .job_leaders()
+            .record_run(job.name, node_id(), Utc::now())
+            .await
+    {
+        tracing::warn!(error = %e, job = job.name, "Failed to record job leader");
+    }
+    outcome
+}
+
+async fn acquire(db: &DbPool, key: i64) -> LeadershipOutcome {
     #[cfg(feature = "database-postgres")]
     {
         let Some(pool) = db.pg_write_pool() else {
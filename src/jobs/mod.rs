@@ -6,8 +6,37 @@
 //!   and orphaned files after a configurable delay.
 //! - **Container Cleanup**: Hard-deletes `expired` / `deleted` containers (and
 //!   their captured `container_files`) after a configurable delay.
+//! - **Vector Store Sync**: Mirrors S3 prefixes, Git repositories and
+//!   Confluence spaces into vector stores, ingesting only changed documents.
+//! - **Scheduled Reports**: Generates and delivers per-org usage, key hygiene
+//!   and guardrail reports on a daily/weekly/monthly schedule.
+//! - **Scheduled Tasks**: Runs organizations' recurring prompts on their cron
+//!   schedules and delivers the answers to a conversation or webhook.
+//! - **Conversation Summaries**: Generates titles and rolling summaries for
+//!   chat conversations with a configurable model.
+//! - **Federation Reporter**: Pushes daily usage totals and provider health
+//!   from a satellite gateway to its federation hub.
+//! - **Circuit Breaker Sync**: Shares circuit breaker opens between replicas
+//!   through the cache for providers in shared mode.
+//! - **SLO Metrics**: Refreshes SLO compliance and burn rate gauges as their
+//!   rolling windows move.
+//! - **Anomaly Detection**: Compares recent per-key and per-org usage against
+//!   a rolling baseline and records spend spikes, model mix shifts and new
+//!   referers, optionally rate limiting the key.
 //! - **Provider Health Checks**: Periodically checks provider availability and
 //!   publishes health status changes to the EventBus.
+//! - **Provider Settings Sync**: Reloads the runtime settings of static
+//!   providers so changes made on another node take effect.
+//! - **Fine-Tuning Sync**: Polls providers for unfinished fine-tuning jobs,
+//!   recording their usage and registering their models once they finish.
+//! - **Idempotency Cleanup**: Deletes expired `Idempotency-Key` claims and
+//!   their stored responses.
+//! - **Read Replica Monitor**: Sends Postgres reads to the primary while the
+//!   read replica is unreachable or lagging.
+//! - **Access Review Reminders**: Periodically emails organization admins a
+//!   list of their inactive users and unused API keys.
+//! - **Elevation Expiry**: Restores members' previous organization roles once
+//!   their time-boxed role elevations expire.
 //!
 //! Jobs follow a consistent pattern:
 //! 1. Configuration in `config/features.rs` or provider config
@@ -16,6 +45,10 @@
 //! 4. Structured result type for tracking state
 //! 5. Metrics/events for monitoring operations
 //!
+//! Retention, vector store cleanup, model catalog sync and DLQ retry also
+//! register with the [`JobScheduler`], which backs `/admin/v1/jobs`: last and
+//! next runs, manual triggers, pausing and run history.
+//!
 //! # Example
 //!
 //! ```toml
@@ -30,35 +63,97 @@
 //! interval_secs = 60
 //! ```
 
+#[cfg(feature = "server")]
+mod access_review_reminders;
+#[cfg(feature = "server")]
+mod anomaly_detection;
 #[cfg(feature = "server")]
 mod background_responses;
 #[cfg(feature = "server")]
+mod circuit_breaker_sync;
+#[cfg(feature = "server")]
 mod containers_cleanup;
 #[cfg(feature = "server")]
 mod containers_reaper;
-mod leader_lock;
+#[cfg(feature = "server")]
+mod conversation_summaries;
+#[cfg(feature = "server")]
+mod elevation_expiry;
+#[cfg(feature = "server")]
+mod federation_reporter;
+#[cfg(feature = "server")]
+mod fine_tuning_sync;
+#[cfg(feature = "server")]
+mod idempotency_cleanup;
+pub(crate) mod leader_lock;
+#[cfg(feature = "server")]
+mod model_catalog_entries_sync;
 mod model_catalog_sync;
 mod oauth_code_cleanup;
 mod provider_health_check;
 #[cfg(feature = "server")]
+mod provider_settings_sync;
+#[cfg(all(feature = "server", feature = "database-postgres"))]
+mod replica_monitor;
+#[cfg(feature = "server")]
 mod responses_cancel_poller;
 #[cfg(feature = "server")]
 mod responses_retention;
+#[cfg(feature = "server")]
+mod scheduled_reports;
+#[cfg(feature = "server")]
+mod scheduled_tasks;
+mod scheduler;
+#[cfg(feature = "server")]
+mod slo_metrics;
 mod vector_store_cleanup;
+#[cfg(feature = "server")]
+mod vector_store_sync;
 
+#[cfg(feature = "server")]
+pub use access_review_reminders::start_access_review_reminders_worker;
+#[cfg(feature = "server")]
+pub use anomaly_detection::start_anomaly_detection_worker;
 #[cfg(feature = "server")]
 pub use background_responses::start_background_response_worker;
 #[cfg(feature = "server")]
+pub use circuit_breaker_sync::start_circuit_breaker_sync_worker;
+#[cfg(feature = "server")]
 pub use containers_cleanup::start_containers_cleanup_worker;
 #[cfg(feature = "server")]
 pub use containers_reaper::start_containers_reaper_worker;
+#[cfg(feature = "server")]
+pub use conversation_summaries::start_conversation_summaries_worker;
+#[cfg(feature = "server")]
+pub use elevation_expiry::start_elevation_expiry_worker;
+#[cfg(feature = "server")]
+pub use federation_reporter::start_federation_reporter_worker;
+#[cfg(feature = "server")]
+pub use fine_tuning_sync::start_fine_tuning_sync_worker;
+#[cfg(feature = "server")]
+pub use idempotency_cleanup::start_idempotency_cleanup_worker;
+#[cfg(feature = "server")]
+pub use model_catalog_entries_sync::start_model_catalog_entries_sync_worker;
 pub use model_catalog_sync::start_model_catalog_sync_worker;
 pub use oauth_code_cleanup::start_oauth_code_cleanup_worker;
 pub use provider_health_check::{
     ProviderHealthChecker, ProviderHealthState, ProviderHealthStateRegistry,
 };
 #[cfg(feature = "server")]
+pub use provider_settings_sync::start_provider_settings_sync_worker;
+#[cfg(all(feature = "server", feature = "database-postgres"))]
+pub use replica_monitor::start_replica_monitor;
+#[cfg(feature = "server")]
 pub use responses_cancel_poller::start_responses_cancel_poller;
 #[cfg(feature = "server")]
 pub use responses_retention::start_responses_retention_worker;
+#[cfg(feature = "server")]
+pub use scheduled_reports::start_scheduled_reports_worker;
+#[cfg(feature = "server")]
+pub use scheduled_tasks::start_scheduled_tasks_worker;
+pub use scheduler::{JobHandle, JobScheduler, JobStatus, RUN_HISTORY_LIMIT};
+#[cfg(feature = "server")]
+pub use slo_metrics::start_slo_metrics_worker;
 pub use vector_store_cleanup::start_vector_store_cleanup_worker;
+#[cfg(feature = "server")]
+pub use vector_store_sync::start_vector_store_sync_worker;
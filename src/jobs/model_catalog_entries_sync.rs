@@ -0,0 +1,37 @@
+//! Reloads custom model catalog entries from the database.
+//!
+//! The node that handles `/admin/v1/model-catalog` applies a change straight
+//! away; every other node picks it up on its next reload.
+
+use std::{sync::Arc, time::Duration};
+
+use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
+
+use crate::{catalog::ModelCatalogRegistry, db::DbPool};
+
+/// How often entries are reloaded. This bounds how long other nodes keep
+/// using the old entries after a change.
+const SYNC_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Spawnable entry point. Exits when `shutdown` is cancelled.
+pub async fn start_model_catalog_entries_sync_worker(
+    db: Arc<DbPool>,
+    registry: ModelCatalogRegistry,
+    shutdown: CancellationToken,
+) {
+    tracing::info!("Starting custom model catalog entry sync");
+    loop {
+        tokio::select! {
+            _ = shutdown.cancelled() => {
+                tracing::info!("Custom model catalog entry sync received shutdown signal");
+                return;
+            }
+            _ = sleep(SYNC_INTERVAL) => {}
+        }
+
+        if let Err(e) = registry.load_custom_entries(&db).await {
+            tracing::warn!(error = %e, "Failed to reload custom model catalog entries");
+        }
+    }
+}
@@ -9,11 +9,14 @@
 //! - The embedded catalog serves as a fallback when sync fails
 //! - Initial sync runs immediately on startup
 
-use std::time::Instant;
+use std::{sync::Arc, time::Instant};
 
 use reqwest::Client;
 
-use crate::{catalog::ModelCatalogRegistry, config::ModelCatalogConfig};
+use crate::{
+    catalog::ModelCatalogRegistry, config::ModelCatalogConfig, jobs::JobScheduler,
+    models::JobTrigger,
+};
 
 /// Results from a single sync run.
 #[derive(Debug)]
@@ -26,12 +29,14 @@ pub struct SyncRunResult {
 
 /// Starts the model catalog sync worker as a background task.
 ///
-/// The worker runs in a loop, fetching the catalog at the configured interval.
+/// The worker runs in a loop, fetching the catalog at the configured interval
+/// or when triggered through the jobs admin API.
 /// It will run indefinitely until the task is cancelled.
 pub async fn start_model_catalog_sync_worker(
     registry: ModelCatalogRegistry,
     config: ModelCatalogConfig,
     http_client: Client,
+    scheduler: Arc<JobScheduler>,
 ) {
     if !config.enabled {
         tracing::info!("Model catalog sync worker disabled by configuration");
@@ -44,44 +49,57 @@ pub async fn start_model_catalog_sync_worker(
         "Starting model catalog sync worker"
     );
 
-    let interval = std::time::Duration::from_secs(config.sync_interval_secs);
+    let job = scheduler.register(
+        "model_catalog_sync",
+        std::time::Duration::from_secs(config.sync_interval_secs),
+    );
 
     // Run initial sync immediately
-    match run_sync(&registry, &config, &http_client).await {
-        Ok(result) => {
-            tracing::info!(
-                model_count = result.model_count,
-                duration_ms = result.duration_ms,
-                "Initial model catalog sync complete"
-            );
-        }
-        Err(e) => {
-            tracing::warn!(
-                error = %e,
-                "Initial model catalog sync failed, using embedded catalog"
-            );
-        }
-    }
+    let mut trigger = JobTrigger::Scheduled;
+    let mut initial = true;
 
-    // Then run at configured interval
     loop {
-        tokio::time::sleep(interval).await;
-
-        match run_sync(&registry, &config, &http_client).await {
-            Ok(result) => {
-                tracing::debug!(
-                    model_count = result.model_count,
-                    duration_ms = result.duration_ms,
-                    "Model catalog sync complete"
-                );
-            }
-            Err(e) => {
-                tracing::warn!(
-                    error = %e,
-                    "Model catalog sync failed, keeping existing data"
-                );
+        if job.should_run(trigger).await {
+            let result = job
+                .run(
+                    trigger,
+                    run_sync(&registry, &config, &http_client),
+                    |result| Some(format!("{} models", result.model_count)),
+                )
+                .await;
+            match (result, initial) {
+                (Ok(result), true) => {
+                    tracing::info!(
+                        model_count = result.model_count,
+                        duration_ms = result.duration_ms,
+                        "Initial model catalog sync complete"
+                    );
+                }
+                (Ok(result), false) => {
+                    tracing::debug!(
+                        model_count = result.model_count,
+                        duration_ms = result.duration_ms,
+                        "Model catalog sync complete"
+                    );
+                }
+                (Err(e), true) => {
+                    tracing::warn!(
+                        error = %e,
+                        "Initial model catalog sync failed, using embedded catalog"
+                    );
+                }
+                (Err(e), false) => {
+                    tracing::warn!(
+                        error = %e,
+                        "Model catalog sync failed, keeping existing data"
+                    );
+                }
             }
+            initial = false;
         }
+
+        // Then run at configured interval
+        trigger = job.tick().await;
     }
 }
 
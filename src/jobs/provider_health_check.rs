@@ -9,6 +9,10 @@
 //! Health status is stored and can be queried via the admin API. Health changes
 //! are published to the EventBus for real-time monitoring.
 //!
+//! Providers with a `probe` section also run scheduled canary completions
+//! against each probed model. Their latency and correctness are recorded as
+//! metrics and, with a database, stored for the provider stats history.
+//!
 //! # Configuration
 //!
 //! Health checks are configured per-provider:
@@ -31,12 +35,17 @@ use chrono::{DateTime, Utc};
 use serde::Serialize;
 
 use crate::{
+    config::{HealthRoutingConfig, ProviderProbeConfig},
+    db::DbPool,
     events::{EventBus, ServerEvent},
+    models::CreateProviderProbeResult,
+    observability::metrics,
     providers::{
         CircuitBreakerRegistry, Provider,
         health_check::{
             HealthCheckResult, HealthStatus, ProviderHealthCheckConfig, ProviderHealthCheckMode,
         },
+        health_check_payload,
     },
 };
 
@@ -62,6 +71,9 @@ pub struct ProviderHealthState {
     pub consecutive_failures: u32,
     /// Number of consecutive successes.
     pub consecutive_successes: u32,
+    /// Share of traffic routed to the provider (0 to 1) when health-based
+    /// routing is enabled. Always 1 otherwise.
+    pub routing_weight: f64,
 }
 
 impl ProviderHealthState {
@@ -76,6 +88,7 @@ impl ProviderHealthState {
             last_check: Utc::now(),
             consecutive_failures: 0,
             consecutive_successes: 0,
+            routing_weight: 1.0,
         }
     }
 
@@ -115,6 +128,39 @@ impl ProviderHealthState {
         }
     }
 
+    /// Move the routing weight after a check. Returns the new weight and why
+    /// it moved, or `None` if it didn't.
+    fn adjust_routing_weight(
+        &mut self,
+        result: &HealthCheckResult,
+        config: &HealthRoutingConfig,
+    ) -> Option<(f64, &'static str)> {
+        if !config.enabled {
+            return None;
+        }
+        let degraded = config
+            .degraded_latency_ms
+            .is_some_and(|limit| result.latency_ms > limit);
+        let (weight, reason) = match result.status {
+            HealthStatus::Unhealthy => (0.0, "unhealthy"),
+            HealthStatus::Healthy if degraded => {
+                // Recover gradually here too, but only up to the degraded cap
+                let restored = self.routing_weight + config.recovery_step;
+                (restored.min(config.degraded_weight), "degraded")
+            }
+            HealthStatus::Healthy => {
+                let restored = self.routing_weight + config.recovery_step;
+                (restored.min(1.0), "recovering")
+            }
+            HealthStatus::Unknown => return None,
+        };
+        if (weight - self.routing_weight).abs() < f64::EPSILON {
+            return None;
+        }
+        self.routing_weight = weight;
+        Some((weight, reason))
+    }
+
     /// Check if status changed from the previous state.
     fn status_changed(&self, previous: HealthStatus) -> bool {
         self.status != previous && previous != HealthStatus::Unknown
@@ -167,6 +213,14 @@ impl ProviderHealthStateRegistry {
         state.values().cloned().collect()
     }
 
+    /// Share of traffic to route to a provider, from 0 to 1.
+    ///
+    /// Providers without health checks or health-based routing get 1.
+    pub fn routing_weight(&self, provider: &str) -> f64 {
+        let state = self.state.read().expect("RwLock poisoned");
+        state.get(provider).map_or(1.0, |s| s.routing_weight)
+    }
+
     /// Check if any providers are registered.
     pub fn is_empty(&self) -> bool {
         let state = self.state.read().expect("RwLock poisoned");
@@ -198,6 +252,23 @@ impl ProviderHealthStateRegistry {
             false
         }
     }
+
+    /// Update a provider's routing weight from a check result (internal use).
+    ///
+    /// Returns the previous weight, new weight, and reason if it changed.
+    fn update_routing_weight(
+        &self,
+        provider: &str,
+        result: &HealthCheckResult,
+        config: &HealthRoutingConfig,
+    ) -> Option<(f64, f64, &'static str)> {
+        let mut state = self.state.write().expect("RwLock poisoned");
+        let provider_state = state.get_mut(provider)?;
+        let previous = provider_state.routing_weight;
+        provider_state
+            .adjust_routing_weight(result, config)
+            .map(|(weight, reason)| (previous, weight, reason))
+    }
 }
 
 impl std::fmt::Debug for ProviderHealthStateRegistry {
@@ -243,6 +314,8 @@ pub struct ProviderHealthChecker {
     event_bus: Option<Arc<EventBus>>,
     /// Circuit breaker registry for recording health check results.
     circuit_breakers: CircuitBreakerRegistry,
+    /// Database for storing probe results.
+    db: Option<Arc<DbPool>>,
 }
 
 impl ProviderHealthChecker {
@@ -293,9 +366,17 @@ impl ProviderHealthChecker {
             client,
             event_bus,
             circuit_breakers,
+            db: None,
         }
     }
 
+    /// Store probe results in the database so they appear in the provider
+    /// stats history.
+    pub fn with_database(mut self, db: Arc<DbPool>) -> Self {
+        self.db = Some(db);
+        self
+    }
+
     /// Register a provider for health checking.
     ///
     /// Only providers with `health_check.enabled = true` in their config should
@@ -392,6 +473,7 @@ impl ProviderHealthChecker {
         let event_bus = self.event_bus;
         let client = self.client;
         let circuit_breakers = self.circuit_breakers;
+        let db = self.db;
 
         // Spawn a task for each provider
         let mut handles = Vec::new();
@@ -399,6 +481,19 @@ impl ProviderHealthChecker {
         for (name, entry) in self.providers {
             let provider = entry.provider;
             let config = entry.config;
+
+            if let Some(probe) = config.probe.clone() {
+                let models = probe_models(&probe, &config, provider.as_ref());
+                let name = name.clone();
+                let provider = provider.clone();
+                let client = client.clone();
+                let db = db.clone();
+                let timeout = config.timeout();
+                handles.push(tokio::spawn(async move {
+                    run_probe_loop(name, provider, probe, models, timeout, client, db).await;
+                }));
+            }
+
             let registry = registry.clone();
             let event_bus = event_bus.clone();
             let client = client.clone();
@@ -568,6 +663,28 @@ async fn run_single_health_check(
         }
     }
 
+    if let Some((previous_weight, weight, reason)) =
+        registry.update_routing_weight(name, &result, &config.routing)
+    {
+        tracing::info!(
+            provider = %name,
+            previous_weight,
+            weight,
+            reason,
+            "Provider routing weight changed"
+        );
+        metrics::record_provider_routing_weight(name, weight);
+        if let Some(bus) = event_bus {
+            bus.publish(ServerEvent::ProviderRoutingWeightChanged {
+                provider: name.to_string(),
+                timestamp: Utc::now(),
+                previous_weight,
+                weight,
+                reason: reason.to_string(),
+            });
+        }
+    }
+
     // Publish event if status changed
     if status_changed && let Some(bus) = event_bus {
         bus.publish(ServerEvent::ProviderHealthChanged {
@@ -580,6 +697,177 @@ async fn run_single_health_check(
     }
 }
 
+/// Models a provider's probes run against: the configured list, else the
+/// health check model, else the provider's default.
+fn probe_models(
+    probe: &ProviderProbeConfig,
+    config: &ProviderHealthCheckConfig,
+    provider: &dyn Provider,
+) -> Vec<String> {
+    if !probe.models.is_empty() {
+        return probe.models.clone();
+    }
+    config
+        .model
+        .as_deref()
+        .or_else(|| provider.default_health_check_model())
+        .map(|m| vec![m.to_string()])
+        .unwrap_or_default()
+}
+
+/// Run the probe loop for a single provider.
+async fn run_probe_loop(
+    name: String,
+    provider: Arc<dyn Provider>,
+    probe: ProviderProbeConfig,
+    models: Vec<String>,
+    timeout: std::time::Duration,
+    client: reqwest::Client,
+    db: Option<Arc<DbPool>>,
+) {
+    if models.is_empty() {
+        tracing::warn!(
+            provider = %name,
+            "No probe model configured and provider has no default; configure [providers.<name>.health_check.probe.models]"
+        );
+        return;
+    }
+
+    tracing::debug!(
+        provider = %name,
+        models = ?models,
+        interval_secs = probe.interval_secs,
+        "Starting probe loop"
+    );
+
+    loop {
+        for model in &models {
+            let result = run_probe(&name, &provider, model, &probe, timeout, &client).await;
+
+            let outcome = match (result.success, result.correct) {
+                (false, _) => "error",
+                (true, true) => "correct",
+                (true, false) => "incorrect",
+            };
+            metrics::record_provider_probe(
+                &name,
+                model,
+                outcome,
+                result.latency_ms as f64 / 1000.0,
+            );
+            if result.success {
+                tracing::debug!(
+                    provider = %name,
+                    model = %model,
+                    latency_ms = result.latency_ms,
+                    correct = result.correct,
+                    output_tokens = ?result.output_tokens,
+                    "Provider probe completed"
+                );
+            } else {
+                tracing::warn!(
+                    provider = %name,
+                    model = %model,
+                    latency_ms = result.latency_ms,
+                    error = ?result.error,
+                    "Provider probe failed"
+                );
+            }
+
+            if let Some(db) = &db
+                && let Err(e) = db.provider_probes().create(result).await
+            {
+                tracing::warn!(provider = %name, error = %e, "Failed to store probe result");
+            }
+        }
+
+        if let Some(db) = &db {
+            let cutoff = Utc::now() - chrono::Duration::days(i64::from(probe.retention_days));
+            if let Err(e) = db.provider_probes().delete_before(&name, cutoff).await {
+                tracing::warn!(provider = %name, error = %e, "Failed to prune probe results");
+            }
+        }
+
+        tokio::time::sleep(probe.interval()).await;
+    }
+}
+
+/// Send one canary completion and time it end to end, including reading
+/// the response body.
+async fn run_probe(
+    name: &str,
+    provider: &Arc<dyn Provider>,
+    model: &str,
+    probe: &ProviderProbeConfig,
+    timeout: std::time::Duration,
+    client: &reqwest::Client,
+) -> CreateProviderProbeResult {
+    let start = Instant::now();
+    let payload = health_check_payload(model, &probe.prompt, probe.max_tokens);
+
+    let body = tokio::time::timeout(timeout, async {
+        let response = provider
+            .create_chat_completion(client, payload)
+            .await
+            .map_err(|e| e.to_string())?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("HTTP {}", status.as_u16()));
+        }
+        axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .unwrap_or_else(|_| Err(format!("Probe timed out after {}s", timeout.as_secs())));
+    let latency_ms = start.elapsed().as_millis() as i64;
+
+    let mut result = CreateProviderProbeResult {
+        provider: name.to_string(),
+        model: model.to_string(),
+        success: false,
+        correct: false,
+        latency_ms,
+        input_tokens: None,
+        output_tokens: None,
+        error: None,
+    };
+    match body {
+        Ok(body) => {
+            let (correct, input_tokens, output_tokens) =
+                evaluate_probe_response(&body, &probe.expected);
+            result.success = true;
+            result.correct = correct;
+            result.input_tokens = input_tokens;
+            result.output_tokens = output_tokens;
+        }
+        Err(e) => result.error = Some(e),
+    }
+    result
+}
+
+/// Check a chat completion body for the expected answer (case-insensitive)
+/// and read its token usage.
+fn evaluate_probe_response(body: &[u8], expected: &str) -> (bool, Option<i64>, Option<i64>) {
+    let Ok(json) = serde_json::from_slice::<serde_json::Value>(body) else {
+        return (false, None, None);
+    };
+    let correct = json
+        .pointer("/choices/0/message/content")
+        .and_then(|c| c.as_str())
+        .is_some_and(|content| {
+            content
+                .to_lowercase()
+                .contains(&expected.trim().to_lowercase())
+        });
+    let tokens = |field: &str| json.pointer(field).and_then(|t| t.as_i64());
+    (
+        correct,
+        tokens("/usage/prompt_tokens"),
+        tokens("/usage/completion_tokens"),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -768,6 +1056,118 @@ mod tests {
         assert_eq!(all_health.len(), 2);
     }
 
+    #[test]
+    fn test_evaluate_probe_response() {
+        let body = serde_json::to_vec(&serde_json::json!({
+            "choices": [{"message": {"role": "assistant", "content": "Ok."}}],
+            "usage": {"prompt_tokens": 14, "completion_tokens": 2}
+        }))
+        .unwrap();
+        assert_eq!(
+            evaluate_probe_response(&body, "OK"),
+            (true, Some(14), Some(2))
+        );
+        assert_eq!(
+            evaluate_probe_response(&body, "PONG"),
+            (false, Some(14), Some(2))
+        );
+        assert_eq!(
+            evaluate_probe_response(b"not json", "OK"),
+            (false, None, None)
+        );
+    }
+
+    #[test]
+    fn test_probe_models_fall_back_to_health_check_model() {
+        let provider = test_provider();
+        let mut probe = ProviderProbeConfig::default();
+        let mut config = ProviderHealthCheckConfig::default();
+        assert_eq!(
+            probe_models(&probe, &config, provider.as_ref()),
+            vec!["test-model"]
+        );
+
+        config.model = Some("gpt-4o-mini".to_string());
+        assert_eq!(
+            probe_models(&probe, &config, provider.as_ref()),
+            vec!["gpt-4o-mini"]
+        );
+
+        probe.models = vec!["gpt-4o".to_string(), "o1".to_string()];
+        assert_eq!(
+            probe_models(&probe, &config, provider.as_ref()),
+            vec!["gpt-4o", "o1"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_probe_records_latency_and_correctness() {
+        let provider = test_provider();
+        let client = reqwest::Client::new();
+        let timeout = std::time::Duration::from_secs(5);
+        let probe = ProviderProbeConfig {
+            expected: "test response".to_string(),
+            ..Default::default()
+        };
+
+        let result = run_probe("test", &provider, "test-model", &probe, timeout, &client).await;
+        assert!(result.success);
+        assert!(result.correct);
+        assert_eq!(result.provider, "test");
+        assert_eq!(result.input_tokens, Some(10));
+        assert_eq!(result.output_tokens, Some(10));
+        assert!(result.error.is_none());
+
+        let probe = ProviderProbeConfig::default();
+        let result = run_probe("test", &provider, "test-model", &probe, timeout, &client).await;
+        assert!(result.success);
+        assert!(!result.correct);
+    }
+
+    #[test]
+    fn test_routing_weight_drops_and_recovers() {
+        let config = HealthRoutingConfig {
+            enabled: true,
+            degraded_latency_ms: Some(1000),
+            degraded_weight: 0.25,
+            recovery_step: 0.5,
+        };
+        let mut state = ProviderHealthState::new("test".to_string());
+        let healthy = HealthCheckResult::healthy(100, 200);
+        let slow = HealthCheckResult::healthy(2000, 200);
+        let failed = HealthCheckResult::unhealthy(100, "Connection refused", None);
+
+        // Already at full weight
+        assert_eq!(state.adjust_routing_weight(&healthy, &config), None);
+
+        assert_eq!(
+            state.adjust_routing_weight(&failed, &config),
+            Some((0.0, "unhealthy"))
+        );
+        // Slow checks recover only up to the degraded cap
+        assert_eq!(
+            state.adjust_routing_weight(&slow, &config),
+            Some((0.25, "degraded"))
+        );
+        assert_eq!(state.adjust_routing_weight(&slow, &config), None);
+        assert_eq!(
+            state.adjust_routing_weight(&healthy, &config),
+            Some((0.75, "recovering"))
+        );
+        assert_eq!(
+            state.adjust_routing_weight(&healthy, &config),
+            Some((1.0, "recovering"))
+        );
+
+        // Disabled: weight stays at 1
+        let mut state = ProviderHealthState::new("test".to_string());
+        assert_eq!(
+            state.adjust_routing_weight(&failed, &HealthRoutingConfig::default()),
+            None
+        );
+        assert_eq!(state.routing_weight, 1.0);
+    }
+
     #[test]
     fn test_provider_health_state_serialization() {
         let state = ProviderHealthState {
@@ -779,6 +1179,7 @@ mod tests {
             last_check: Utc::now(),
             consecutive_failures: 0,
             consecutive_successes: 5,
+            routing_weight: 1.0,
         };
 
         let json = serde_json::to_string(&state).unwrap();
@@ -1043,6 +1444,8 @@ mod tests {
             timeout_secs: 5,
             model: None,
             prompt: None,
+            routing: HealthRoutingConfig::default(),
+            probe: None,
         }
     }
 
@@ -1146,6 +1549,8 @@ mod tests {
             timeout_secs: 1,
             model: None,
             prompt: None,
+            routing: HealthRoutingConfig::default(),
+            probe: None,
         };
 
         checker.register("test-provider", provider, config);
@@ -1291,6 +1696,8 @@ mod tests {
             timeout_secs: 1, // Short timeout
             model: None,
             prompt: None,
+            routing: HealthRoutingConfig::default(),
+            probe: None,
         };
 
         checker.register("slow-provider", provider, config);
@@ -1410,6 +1817,8 @@ mod tests {
             timeout_secs: 5,
             model: Some("test-model".to_string()),
             prompt: None,
+            routing: HealthRoutingConfig::default(),
+            probe: None,
         };
 
         checker.register("inference-cb-provider", provider, config);
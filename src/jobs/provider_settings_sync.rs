@@ -0,0 +1,37 @@
+//! Reloads runtime provider settings from the database.
+//!
+//! The node that handles `/admin/v1/providers/{name}/settings` applies a
+//! change straight away; every other node picks it up on its next reload.
+
+use std::{sync::Arc, time::Duration};
+
+use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
+
+use crate::{db::DbPool, providers::ProviderSettingsRegistry};
+
+/// How often settings are reloaded. This bounds how long other nodes keep
+/// using the old settings after a change.
+const SYNC_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Spawnable entry point. Exits when `shutdown` is cancelled.
+pub async fn start_provider_settings_sync_worker(
+    db: Arc<DbPool>,
+    settings: ProviderSettingsRegistry,
+    shutdown: CancellationToken,
+) {
+    tracing::info!("Starting provider settings sync");
+    loop {
+        tokio::select! {
+            _ = shutdown.cancelled() => {
+                tracing::info!("Provider settings sync received shutdown signal");
+                return;
+            }
+            _ = sleep(SYNC_INTERVAL) => {}
+        }
+
+        if let Err(e) = settings.load(&db).await {
+            tracing::warn!(error = %e, "Failed to reload provider settings");
+        }
+    }
+}
@@ -0,0 +1,39 @@
+//! Checks the Postgres read replica and routes reads to the primary while
+//! it's unreachable or lagging.
+//!
+//! Every node runs its own monitor, since each node routes its own reads and
+//! may see the replica differently.
+
+use std::{sync::Arc, time::Duration};
+
+use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
+
+use crate::db::DbPool;
+
+/// Spawnable entry point. Exits when `shutdown` is cancelled.
+pub async fn start_replica_monitor(
+    db: Arc<DbPool>,
+    max_lag_secs: u64,
+    interval: Duration,
+    shutdown: CancellationToken,
+) {
+    tracing::info!(
+        max_lag_secs,
+        interval_secs = interval.as_secs(),
+        "Starting read replica monitor"
+    );
+    loop {
+        tokio::select! {
+            _ = shutdown.cancelled() => {
+                tracing::info!("Read replica monitor received shutdown signal");
+                return;
+            }
+            _ = sleep(interval) => {}
+        }
+
+        if db.check_replica(max_lag_secs).await.is_none() {
+            return;
+        }
+    }
+}
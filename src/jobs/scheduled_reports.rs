@@ -0,0 +1,376 @@
+//! Scheduled report worker.
+//!
+//! On every tick the worker walks each report in
+//! `[features.scheduled_reports]`, works out the most recent complete
+//! period for its schedule, and generates + delivers the report for every
+//! target organization that hasn't received it yet.
+//!
+//! Delivery state lives in `scheduled_report_runs`, so the worker is
+//! restart-safe: a period is skipped once it has a `delivered` run, and
+//! retried on later ticks after a failure until `max_attempts` is reached.
+//! Ticks are short compared to the schedules, so a report lands within
+//! `interval_secs` of its period closing.
+
+use std::{sync::Arc, time::Instant};
+
+use chrono::{DateTime, Utc};
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    config::{ScheduledReportConfig, ScheduledReportsConfig},
+    db::{DbPool, DbResult, ListParams},
+    jobs::leader_lock::{self, LeadershipOutcome, keys},
+    models::{CreateReportRun, Organization, ReportRun, ReportRunStatus},
+    observability::metrics,
+    services::{ReportPeriod, ScheduledReportService, scheduled_reports::render},
+};
+
+/// Results from a single scheduling pass.
+#[derive(Debug, Default)]
+pub struct ReportRunResult {
+    /// Reports delivered this pass.
+    pub delivered: u64,
+    /// Attempts that failed this pass (will be retried if attempts remain).
+    pub failed: u64,
+    /// Duration of the pass in milliseconds.
+    pub duration_ms: u64,
+}
+
+/// Starts the scheduled report worker as a background task.
+pub async fn start_scheduled_reports_worker(
+    service: ScheduledReportService,
+    db: Arc<DbPool>,
+    config: ScheduledReportsConfig,
+    shutdown: CancellationToken,
+) {
+    if !config.enabled || config.reports.is_empty() {
+        tracing::info!("Scheduled reports worker disabled by configuration");
+        return;
+    }
+
+    tracing::info!(
+        interval_secs = config.interval_secs,
+        reports = config.reports.len(),
+        "Starting scheduled reports worker"
+    );
+
+    let interval = config.interval();
+
+    loop {
+        if shutdown.is_cancelled() {
+            tracing::info!("Scheduled reports worker received shutdown signal");
+            return;
+        }
+        // Only one replica may deliver per tick, otherwise every replica
+        // would mail the same report.
+        let _guard = match leader_lock::try_acquire(&db, keys::SCHEDULED_REPORTS).await {
+            LeadershipOutcome::Leader(g) => Some(g),
+            LeadershipOutcome::NotLeader => {
+                tracing::trace!("scheduled_reports: not leader this tick, skipping");
+                tokio::select! {
+                    _ = shutdown.cancelled() => return,
+                    _ = tokio::time::sleep(interval) => {}
+                }
+                continue;
+            }
+            LeadershipOutcome::NoCoordination => None,
+        };
+
+        match run_scheduled_reports(&service, &db, &config, Utc::now()).await {
+            Ok(result) if result.delivered > 0 || result.failed > 0 => {
+                tracing::info!(
+                    delivered = result.delivered,
+                    failed = result.failed,
+                    duration_ms = result.duration_ms,
+                    "Scheduled reports pass complete"
+                );
+            }
+            Ok(_) => {
+                tracing::debug!("Scheduled reports pass complete, nothing due");
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Error running scheduled reports");
+            }
+        }
+
+        tokio::select! {
+            _ = shutdown.cancelled() => return,
+            _ = tokio::time::sleep(interval) => {}
+        }
+    }
+}
+
+/// Run one scheduling pass: deliver every report/org pair that is due.
+async fn run_scheduled_reports(
+    service: &ScheduledReportService,
+    db: &Arc<DbPool>,
+    config: &ScheduledReportsConfig,
+    now: DateTime<Utc>,
+) -> DbResult<ReportRunResult> {
+    let start = Instant::now();
+    let mut result = ReportRunResult::default();
+    let today = now.date_naive();
+
+    for report in &config.reports {
+        let period = ReportPeriod::previous(report.schedule, today);
+
+        for org in target_orgs(db, report).await? {
+            let attempts = db
+                .scheduled_report_runs()
+                .list_for_period(&report.name, org.id, period.start)
+                .await?;
+            if !is_due(&attempts, config.max_attempts) {
+                continue;
+            }
+
+            let run = run_one(service, report, &org, period, now).await;
+            metrics::record_scheduled_report(report.kind.as_str(), run.status.as_str());
+            match run.status {
+                ReportRunStatus::Delivered => {
+                    result.delivered += 1;
+                    tracing::info!(
+                        report = %report.name,
+                        org = %org.slug,
+                        period_start = %period.start,
+                        "Scheduled report delivered"
+                    );
+                }
+                ReportRunStatus::Failed => {
+                    result.failed += 1;
+                    tracing::warn!(
+                        report = %report.name,
+                        org = %org.slug,
+                        period_start = %period.start,
+                        attempt = attempts.len() + 1,
+                        max_attempts = config.max_attempts,
+                        error = run.error.as_deref().unwrap_or_default(),
+                        "Scheduled report failed"
+                    );
+                }
+            }
+            db.scheduled_report_runs().create(run).await?;
+        }
+    }
+
+    result.duration_ms = start.elapsed().as_millis() as u64;
+    Ok(result)
+}
+
+/// Generate, render and deliver one report. Never fails: errors are
+/// captured in the returned run record.
+async fn run_one(
+    service: &ScheduledReportService,
+    report: &ScheduledReportConfig,
+    org: &Organization,
+    period: ReportPeriod,
+    now: DateTime<Utc>,
+) -> CreateReportRun {
+    let mut run = CreateReportRun {
+        report_name: report.name.clone(),
+        kind: report.kind,
+        org_id: org.id,
+        period_start: period.start,
+        period_end: period.end,
+        format: report.format,
+        delivery: report.delivery.as_str().to_string(),
+        status: ReportRunStatus::Failed,
+        error: None,
+        content: None,
+    };
+
+    let doc = match service.generate(report, org, period, now).await {
+        Ok(doc) => doc,
+        Err(e) => {
+            run.error = Some(e.to_string());
+            return run;
+        }
+    };
+    let rendered = match render(&doc, report.format) {
+        Ok(rendered) => rendered,
+        Err(e) => {
+            run.error = Some(e.to_string());
+            return run;
+        }
+    };
+
+    match service.deliver(report, &doc, &rendered).await {
+        Ok(()) => run.status = ReportRunStatus::Delivered,
+        Err(e) => run.error = Some(e.to_string()),
+    }
+    run.content = Some(rendered);
+    run
+}
+
+/// Whether another attempt should be made for a report period.
+fn is_due(attempts: &[ReportRun], max_attempts: u32) -> bool {
+    let delivered = attempts
+        .iter()
+        .any(|r| r.status == ReportRunStatus::Delivered);
+    !delivered && attempts.len() < max_attempts as usize
+}
+
+/// Organizations a report is sent for: the configured slugs, or every
+/// organization when none are listed. Unknown slugs are logged and skipped.
+async fn target_orgs(
+    db: &Arc<DbPool>,
+    report: &ScheduledReportConfig,
+) -> DbResult<Vec<Organization>> {
+    let mut orgs = Vec::new();
+
+    if !report.orgs.is_empty() {
+        for slug in &report.orgs {
+            match db.organizations().get_by_slug(slug).await? {
+                Some(org) => orgs.push(org),
+                None => tracing::warn!(
+                    report = %report.name,
+                    org = %slug,
+                    "Scheduled report references unknown organization"
+                ),
+            }
+        }
+        return Ok(orgs);
+    }
+
+    let mut params = ListParams {
+        limit: Some(500),
+        ..Default::default()
+    };
+    loop {
+        let page = db.organizations().list(params.clone()).await?;
+        orgs.extend(page.items);
+        match page.cursors.next {
+            Some(next) if page.has_more => params.cursor = Some(next),
+            _ => break,
+        }
+    }
+    Ok(orgs)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::config::{ReportFormat, ReportKind};
+
+    fn run(status: ReportRunStatus) -> ReportRun {
+        ReportRun {
+            id: Uuid::new_v4(),
+            report_name: "weekly".into(),
+            kind: ReportKind::UsageSummary,
+            org_id: Uuid::new_v4(),
+            period_start: NaiveDate::from_ymd_opt(2025, 3, 3).unwrap(),
+            period_end: NaiveDate::from_ymd_opt(2025, 3, 9).unwrap(),
+            format: ReportFormat::Json,
+            delivery: "webhook".into(),
+            status,
+            error: None,
+            content: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_is_due() {
+        assert!(is_due(&[], 3));
+        assert!(is_due(&[run(ReportRunStatus::Failed)], 3));
+        assert!(!is_due(
+            &[
+                run(ReportRunStatus::Failed),
+                run(ReportRunStatus::Delivered)
+            ],
+            3
+        ));
+        assert!(!is_due(
+            &[
+                run(ReportRunStatus::Failed),
+                run(ReportRunStatus::Failed),
+                run(ReportRunStatus::Failed)
+            ],
+            3
+        ));
+    }
+
+    #[cfg(feature = "database-sqlite")]
+    #[tokio::test]
+    async fn test_failed_delivery_is_recorded_and_retried_until_max_attempts() {
+        use crate::{
+            config::{ReportDelivery, ReportSchedule},
+            db::tests::harness::{create_sqlite_pool, run_sqlite_migrations},
+            models::{CreateOrganization, ReportRunFilter},
+        };
+
+        let pool = create_sqlite_pool().await;
+        run_sqlite_migrations(&pool).await;
+        let db = Arc::new(DbPool::from_sqlite(pool));
+        let org = db
+            .organizations()
+            .create(CreateOrganization {
+                slug: "acme".into(),
+                name: "Acme".into(),
+            })
+            .await
+            .unwrap();
+
+        let config = ScheduledReportsConfig {
+            enabled: true,
+            max_attempts: 2,
+            reports: vec![ScheduledReportConfig {
+                name: "weekly".into(),
+                kind: ReportKind::UsageSummary,
+                schedule: ReportSchedule::Weekly,
+                format: ReportFormat::Json,
+                orgs: vec![],
+                // Nothing listens on port 1, so delivery fails fast
+                delivery: ReportDelivery::Webhook {
+                    url: "http://127.0.0.1:1/reports".into(),
+                    bearer_token: None,
+                    signing_secret: None,
+                    timeout_secs: 5,
+                },
+                stale_key_days: 90,
+            }],
+            ..Default::default()
+        };
+        let service = ScheduledReportService::new(db.clone(), reqwest::Client::new());
+        let now = Utc::now();
+
+        for expected_failures in [1, 1, 0] {
+            let result = run_scheduled_reports(&service, &db, &config, now)
+                .await
+                .unwrap();
+            assert_eq!(result.delivered, 0);
+            assert_eq!(result.failed, expected_failures);
+        }
+
+        let runs = db
+            .scheduled_report_runs()
+            .list(
+                ReportRunFilter {
+                    org_id: Some(org.id),
+                    report_name: None,
+                },
+                ListParams::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(runs.items.len(), 2, "stops after max_attempts");
+        assert!(
+            runs.items
+                .iter()
+                .all(|r| r.status == ReportRunStatus::Failed && r.error.is_some())
+        );
+
+        let stored = db
+            .scheduled_report_runs()
+            .get_by_id(runs.items[0].id)
+            .await
+            .unwrap()
+            .unwrap();
+        let content: serde_json::Value =
+            serde_json::from_str(stored.content.as_deref().unwrap()).unwrap();
+        assert_eq!(content["kind"], "usage_summary");
+        assert_eq!(content["org_slug"], "acme");
+    }
+}
@@ -0,0 +1,207 @@
+//! Scheduled tasks worker.
+//!
+//! Every `interval_secs` the worker runs the enabled tasks whose next run is
+//! due, oldest first, up to `batch_size` per pass. Each run is recorded with
+//! the task's next run time in one write, so a task runs at most once per
+//! scheduled time even if the node stops mid-pass. Runs missed while the
+//! gateway was down are not caught up: the next run is the schedule's first
+//! match after the run finishes.
+//!
+//! Failed runs are counted in metrics and published on the event bus. After
+//! `max_consecutive_failures` failures in a row a task is disabled until an
+//! admin enables it again.
+
+use std::time::Instant;
+
+use chrono::Utc;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    AppState,
+    db::{DbError, DbResult},
+    events::ServerEvent,
+    jobs::leader_lock::{self, LeadershipOutcome, keys},
+    models::{ScheduledTask, ScheduledTaskProgress, ScheduledTaskRunStatus},
+    observability::metrics,
+    services::scheduled_tasks,
+};
+
+/// Results from a single pass.
+#[derive(Debug, Default)]
+pub struct ScheduledTasksResult {
+    /// Runs that were generated and delivered.
+    pub succeeded: u64,
+    /// Runs that failed.
+    pub failed: u64,
+    /// Tasks disabled after too many failures in a row.
+    pub disabled: u64,
+    /// Duration of the pass in milliseconds.
+    pub duration_ms: u64,
+}
+
+/// Starts the scheduled tasks worker as a background task.
+pub async fn start_scheduled_tasks_worker(state: AppState, shutdown: CancellationToken) {
+    let config = &state.config.features.scheduled_tasks;
+    if !config.enabled {
+        tracing::info!("Scheduled tasks worker disabled by configuration");
+        return;
+    }
+    let Some(db) = state.db.clone() else {
+        tracing::warn!("Scheduled tasks require a database; worker not started");
+        return;
+    };
+
+    tracing::info!(
+        interval_secs = config.interval_secs,
+        batch_size = config.batch_size,
+        "Starting scheduled tasks worker"
+    );
+
+    let interval = config.interval();
+
+    loop {
+        tokio::select! {
+            _ = shutdown.cancelled() => {
+                tracing::info!("Scheduled tasks worker received shutdown signal");
+                return;
+            }
+            _ = tokio::time::sleep(interval) => {}
+        }
+
+        // Replicas would otherwise run (and bill) the same task twice.
+        let _guard = match leader_lock::try_acquire(&db, keys::SCHEDULED_TASKS).await {
+            LeadershipOutcome::Leader(g) => Some(g),
+            LeadershipOutcome::NotLeader => {
+                tracing::trace!("scheduled_tasks: not leader this tick, skipping");
+                continue;
+            }
+            LeadershipOutcome::NoCoordination => None,
+        };
+
+        match run_scheduled_tasks(&state, &shutdown).await {
+            Ok(result) if result.succeeded > 0 || result.failed > 0 => {
+                tracing::info!(
+                    succeeded = result.succeeded,
+                    failed = result.failed,
+                    disabled = result.disabled,
+                    duration_ms = result.duration_ms,
+                    "Scheduled tasks pass complete"
+                );
+            }
+            Ok(_) => {
+                tracing::debug!("Scheduled tasks pass complete, nothing due");
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Error running scheduled tasks");
+            }
+        }
+    }
+}
+
+/// Run one pass over the tasks that are due.
+async fn run_scheduled_tasks(
+    state: &AppState,
+    shutdown: &CancellationToken,
+) -> DbResult<ScheduledTasksResult> {
+    let Some(db) = state.db.as_ref() else {
+        return Ok(ScheduledTasksResult::default());
+    };
+    let config = &state.config.features.scheduled_tasks;
+    let start = Instant::now();
+    let mut result = ScheduledTasksResult::default();
+
+    let due = db
+        .scheduled_tasks()
+        .list_due(Utc::now(), i64::from(config.batch_size))
+        .await?;
+    for task in due {
+        if shutdown.is_cancelled() {
+            break;
+        }
+        run_one(state, &task, &mut result).await?;
+    }
+
+    result.duration_ms = start.elapsed().as_millis() as u64;
+    Ok(result)
+}
+
+/// Run a task, record the run and schedule the task's next run.
+async fn run_one(
+    state: &AppState,
+    task: &ScheduledTask,
+    result: &mut ScheduledTasksResult,
+) -> DbResult<()> {
+    let Some(db) = state.db.as_ref() else {
+        return Ok(());
+    };
+    let config = &state.config.features.scheduled_tasks;
+
+    let run = scheduled_tasks::run_task(state, task).await;
+    let succeeded = run.status == ScheduledTaskRunStatus::Succeeded;
+    metrics::record_scheduled_task_run(run.status.as_str());
+
+    let consecutive_failures = if succeeded {
+        0
+    } else {
+        task.consecutive_failures + 1
+    };
+    let disable = consecutive_failures >= config.max_consecutive_failures as i32;
+    let progress = ScheduledTaskProgress {
+        next_run_at: if disable {
+            None
+        } else {
+            scheduled_tasks::next_run(task, Utc::now())
+        },
+        consecutive_failures,
+        enabled: !disable,
+    };
+
+    let repo = db.scheduled_tasks();
+    let run = match repo.record_run(run, progress).await {
+        Ok(run) => run,
+        // Deleted while it was running
+        Err(DbError::NotFound) => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    if let Err(e) = repo
+        .prune_runs(task.id, i64::from(config.run_history_limit))
+        .await
+    {
+        tracing::warn!(task_id = %task.id, error = %e, "Failed to prune scheduled task runs");
+    }
+
+    if succeeded {
+        result.succeeded += 1;
+        return Ok(());
+    }
+
+    result.failed += 1;
+    let error = run.error.unwrap_or_default();
+    tracing::warn!(
+        task_id = %task.id,
+        org_id = %task.org_id,
+        run_id = %run.id,
+        consecutive_failures,
+        error = %error,
+        "Scheduled task run failed"
+    );
+    if disable {
+        result.disabled += 1;
+        tracing::warn!(
+            task_id = %task.id,
+            org_id = %task.org_id,
+            "Scheduled task disabled after repeated failures"
+        );
+    }
+    state.event_bus.publish(ServerEvent::ScheduledTaskFailed {
+        task_id: task.id,
+        run_id: run.id,
+        timestamp: run.finished_at,
+        org_id: task.org_id,
+        name: task.name.clone(),
+        error,
+        consecutive_failures,
+        disabled: disable,
+    });
+    Ok(())
+}
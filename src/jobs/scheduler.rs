@@ -0,0 +1,359 @@
+//! Registry of periodic background jobs, behind the `/admin/v1/jobs` API.
+//!
+//! Workers register when they start and drive their loop through the
+//! returned [`JobHandle`]: [`JobHandle::tick`] waits until the next run is
+//! due or the job is triggered through the API, [`JobHandle::should_run`]
+//! skips scheduled runs while the job is paused, and [`JobHandle::run`]
+//! times a run and stores its outcome in `job_runs`.
+//!
+//! Registration, next-run times and manual triggers are per node: a trigger
+//! runs the job on the node that received it. Pausing and run history live
+//! in the database so they cover the whole deployment; without a database
+//! they are kept in memory.
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::Display,
+    future::Future,
+    sync::Arc,
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::Notify;
+
+use super::leader_lock;
+use crate::{
+    compat::Mutex,
+    db::{DbPool, DbResult},
+    models::{CreateJobRun, JobRun, JobRunStatus, JobTrigger},
+};
+
+/// Runs kept in `job_runs` per job; older runs are pruned as new ones land.
+pub const RUN_HISTORY_LIMIT: i64 = 100;
+
+/// A registered job, as reported by `GET /admin/v1/jobs`.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct JobStatus {
+    /// Job name, e.g. `retention`
+    pub name: String,
+    /// Time between scheduled runs
+    pub interval_secs: u64,
+    /// Whether scheduled runs are paused
+    pub paused: bool,
+    /// Whether this node is running the job right now
+    pub running: bool,
+    /// When this node next runs the job
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_run_at: Option<DateTime<Utc>>,
+    /// The most recent run on any node
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_run: Option<JobRun>,
+}
+
+/// Jobs registered on this node.
+pub struct JobScheduler {
+    db: Option<Arc<DbPool>>,
+    jobs: Mutex<BTreeMap<&'static str, Arc<JobEntry>>>,
+    /// Paused jobs and last runs, used when there is no database.
+    local: Mutex<LocalState>,
+}
+
+#[derive(Default)]
+struct LocalState {
+    paused: HashSet<String>,
+    last_runs: HashMap<String, JobRun>,
+}
+
+struct JobEntry {
+    name: &'static str,
+    interval: Duration,
+    trigger: Notify,
+    state: Mutex<EntryState>,
+}
+
+#[derive(Default)]
+struct EntryState {
+    next_run_at: Option<DateTime<Utc>>,
+    running: bool,
+}
+
+impl JobScheduler {
+    pub fn new(db: Option<Arc<DbPool>>) -> Self {
+        Self {
+            db,
+            jobs: Mutex::new(BTreeMap::new()),
+            local: Mutex::new(LocalState::default()),
+        }
+    }
+
+    /// Register a job that runs every `interval`. Registering a name again
+    /// replaces the earlier registration.
+    pub fn register(self: &Arc<Self>, name: &'static str, interval: Duration) -> JobHandle {
+        let entry = Arc::new(JobEntry {
+            name,
+            interval,
+            trigger: Notify::new(),
+            state: Mutex::new(EntryState::default()),
+        });
+        self.jobs.lock().insert(name, Arc::clone(&entry));
+        JobHandle {
+            scheduler: Arc::clone(self),
+            entry,
+        }
+    }
+
+    fn entry(&self, name: &str) -> Option<Arc<JobEntry>> {
+        self.jobs.lock().get(name).cloned()
+    }
+
+    /// All jobs registered on this node, ordered by name.
+    pub async fn list(&self) -> DbResult<Vec<JobStatus>> {
+        let entries: Vec<_> = self.jobs.lock().values().cloned().collect();
+        let paused = self.paused().await?;
+        let mut last_runs: HashMap<String, JobRun> = match &self.db {
+            Some(db) => db
+                .job_runs()
+                .latest()
+                .await?
+                .into_iter()
+                .map(|run| (run.job.clone(), run))
+                .collect(),
+            None => self.local.lock().last_runs.clone(),
+        };
+
+        Ok(entries
+            .iter()
+            .map(|entry| {
+                let state = entry.state.lock();
+                JobStatus {
+                    name: entry.name.to_string(),
+                    interval_secs: entry.interval.as_secs(),
+                    paused: paused.contains(entry.name),
+                    running: state.running,
+                    next_run_at: state.next_run_at,
+                    last_run: last_runs.remove(entry.name),
+                }
+            })
+            .collect())
+    }
+
+    /// Status of one job, or `None` if it isn't registered on this node.
+    pub async fn get(&self, name: &str) -> DbResult<Option<JobStatus>> {
+        if self.entry(name).is_none() {
+            return Ok(None);
+        }
+        Ok(self.list().await?.into_iter().find(|job| job.name == name))
+    }
+
+    /// Most recent runs of a job, newest first.
+    pub async fn history(&self, name: &str, limit: i64) -> DbResult<Vec<JobRun>> {
+        match &self.db {
+            Some(db) => db.job_runs().list(name, limit).await,
+            None => Ok(self
+                .local
+                .lock()
+                .last_runs
+                .get(name)
+                .cloned()
+                .into_iter()
+                .collect()),
+        }
+    }
+
+    /// Run a job on this node as soon as it's idle, even if it's paused.
+    /// Returns false if the job isn't registered here.
+    pub fn trigger(&self, name: &str) -> bool {
+        let Some(entry) = self.entry(name) else {
+            return false;
+        };
+        // `notify_one` keeps a permit when the worker is mid-run, so the
+        // trigger is picked up as soon as the current run finishes.
+        entry.trigger.notify_one();
+        true
+    }
+
+    /// Pause or resume scheduled runs of a job. Returns false if the job
+    /// isn't registered here.
+    pub async fn set_paused(&self, name: &str, paused: bool) -> DbResult<bool> {
+        if self.entry(name).is_none() {
+            return Ok(false);
+        }
+        match (&self.db, paused) {
+            (Some(db), true) => db.job_runs().pause(name, Utc::now()).await?,
+            (Some(db), false) => {
+                db.job_runs().resume(name).await?;
+            }
+            (None, true) => {
+                self.local.lock().paused.insert(name.to_string());
+            }
+            (None, false) => {
+                self.local.lock().paused.remove(name);
+            }
+        }
+        Ok(true)
+    }
+
+    async fn paused(&self) -> DbResult<HashSet<String>> {
+        match &self.db {
+            Some(db) => Ok(db.job_runs().list_paused().await?.into_iter().collect()),
+            None => Ok(self.local.lock().paused.clone()),
+        }
+    }
+
+    async fn record(&self, run: CreateJobRun) {
+        let Some(db) = &self.db else {
+            let run = JobRun {
+                id: uuid::Uuid::new_v4(),
+                job: run.job.clone(),
+                node_id: run.node_id,
+                triggered_by: run.triggered_by,
+                status: run.status,
+                summary: run.summary,
+                error: run.error,
+                started_at: run.started_at,
+                finished_at: run.finished_at,
+                duration_ms: (run.finished_at - run.started_at).num_milliseconds(),
+            };
+            self.local.lock().last_runs.insert(run.job.clone(), run);
+            return;
+        };
+
+        let job = run.job.clone();
+        if let Err(e) = db.job_runs().create(run).await {
+            tracing::warn!(job = %job, error = %e, "Failed to record job run");
+            return;
+        }
+        if let Err(e) = db.job_runs().prune(&job, RUN_HISTORY_LIMIT).await {
+            tracing::warn!(job = %job, error = %e, "Failed to prune job runs");
+        }
+    }
+}
+
+/// A worker's handle on its registered job.
+pub struct JobHandle {
+    scheduler: Arc<JobScheduler>,
+    entry: Arc<JobEntry>,
+}
+
+impl JobHandle {
+    /// Wait until the next scheduled run is due or the job is triggered.
+    pub async fn tick(&self) -> JobTrigger {
+        self.entry.state.lock().next_run_at = chrono::Duration::from_std(self.entry.interval)
+            .ok()
+            .map(|interval| Utc::now() + interval);
+
+        tokio::select! {
+            _ = tokio::time::sleep(self.entry.interval) => JobTrigger::Scheduled,
+            _ = self.entry.trigger.notified() => JobTrigger::Manual,
+        }
+    }
+
+    /// Whether to go ahead with a run: scheduled runs are skipped while the
+    /// job is paused, manual runs never are.
+    pub async fn should_run(&self, trigger: JobTrigger) -> bool {
+        if trigger == JobTrigger::Manual {
+            return true;
+        }
+        match self.scheduler.paused().await {
+            Ok(paused) if paused.contains(self.entry.name) => {
+                tracing::trace!(job = self.entry.name, "Job paused, skipping scheduled run");
+                false
+            }
+            Ok(_) => true,
+            Err(e) => {
+                tracing::warn!(job = self.entry.name, error = %e, "Failed to check whether job is paused");
+                true
+            }
+        }
+    }
+
+    /// Run `work` and record its outcome. `summarize` describes a
+    /// successful run for the history, e.g. how many records it deleted.
+    pub async fn run<T, E: Display>(
+        &self,
+        trigger: JobTrigger,
+        work: impl Future<Output = Result<T, E>>,
+        summarize: impl FnOnce(&T) -> Option<String>,
+    ) -> Result<T, E> {
+        {
+            let mut state = self.entry.state.lock();
+            state.running = true;
+            state.next_run_at = None;
+        }
+        let started_at = Utc::now();
+        let result = work.await;
+        let finished_at = Utc::now();
+        self.entry.state.lock().running = false;
+
+        let (status, summary, error) = match &result {
+            Ok(value) => (JobRunStatus::Succeeded, summarize(value), None),
+            Err(e) => (JobRunStatus::Failed, None, Some(e.to_string())),
+        };
+        self.scheduler
+            .record(CreateJobRun {
+                job: self.entry.name.to_string(),
+                node_id: leader_lock::node_id().to_string(),
+                triggered_by: trigger,
+                status,
+                summary,
+                error,
+                started_at,
+                finished_at,
+            })
+            .await;
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_trigger_pause_and_record_without_database() {
+        let scheduler = Arc::new(JobScheduler::new(None));
+        let job = scheduler.register("retention", Duration::from_secs(3600));
+        assert!(!scheduler.trigger("unknown"));
+
+        // A trigger wakes the worker long before the interval elapses
+        assert!(scheduler.trigger("retention"));
+        assert_eq!(job.tick().await, JobTrigger::Manual);
+
+        assert!(scheduler.set_paused("retention", true).await.unwrap());
+        assert!(!scheduler.set_paused("unknown", true).await.unwrap());
+        assert!(!job.should_run(JobTrigger::Scheduled).await);
+        assert!(job.should_run(JobTrigger::Manual).await);
+
+        let result: Result<u32, String> = job
+            .run(JobTrigger::Manual, async { Ok(3) }, |n| {
+                Some(format!("Deleted {n} records"))
+            })
+            .await;
+        assert_eq!(result, Ok(3));
+        let _ = job
+            .run(
+                JobTrigger::Scheduled,
+                async { Err::<u32, _>("boom".to_string()) },
+                |_| None,
+            )
+            .await;
+
+        let jobs = scheduler.list().await.unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].name, "retention");
+        assert_eq!(jobs[0].interval_secs, 3600);
+        assert!(jobs[0].paused);
+        assert!(!jobs[0].running);
+        let last_run = jobs[0].last_run.as_ref().unwrap();
+        assert_eq!(last_run.status, JobRunStatus::Failed);
+        assert_eq!(last_run.error.as_deref(), Some("boom"));
+
+        assert!(scheduler.set_paused("retention", false).await.unwrap());
+        assert!(job.should_run(JobTrigger::Scheduled).await);
+        assert!(scheduler.get("unknown").await.unwrap().is_none());
+    }
+}
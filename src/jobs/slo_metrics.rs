@@ -0,0 +1,32 @@
+//! Publishes SLO compliance and burn rates as Prometheus gauges.
+//!
+//! Good and bad counts are recorded as requests finish, but compliance and
+//! burn rates are functions of time as well: a window with no new traffic
+//! still slides forward. This worker recomputes them on a fixed interval so
+//! the gauges stay current between requests.
+
+use std::time::Duration;
+
+use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
+
+use crate::observability::slo::SloTracker;
+
+/// How often the gauges are refreshed. Well under the shortest (5m) burn
+/// rate window.
+const PUBLISH_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Spawnable entry point. Exits when `shutdown` is cancelled.
+pub async fn start_slo_metrics_worker(tracker: &'static SloTracker, shutdown: CancellationToken) {
+    tracing::info!("Starting SLO metrics publisher");
+    loop {
+        tracker.publish_metrics();
+        tokio::select! {
+            _ = shutdown.cancelled() => {
+                tracing::info!("SLO metrics publisher received shutdown signal");
+                return;
+            }
+            _ = sleep(PUBLISH_INTERVAL) => {}
+        }
+    }
+}
@@ -1,10 +1,11 @@
 //! Vector store cleanup worker for removing soft-deleted stores.
 //!
 //! This module provides a background worker that periodically:
-//! 1. Finds soft-deleted vector stores that have passed the cleanup delay
-//! 2. Deletes all chunks from the vector database for each store
-//! 3. Removes files that are no longer referenced by any vector store
-//! 4. Hard deletes the vector store record from the database
+//! 1. Marks stores whose `expires_after` policy has lapsed as `expired`
+//! 2. Finds soft-deleted vector stores that have passed the cleanup delay
+//! 3. Deletes all chunks from the vector database for each store
+//! 4. Removes files that are no longer referenced by any vector store
+//! 5. Hard deletes the vector store record from the database
 //!
 //! The cleanup process is designed to be safe and incremental:
 //! - Cleanup is batched to avoid long-running operations
@@ -20,7 +21,11 @@ use crate::{
     cache::vector_store::VectorBackend,
     config::VectorStoreCleanupConfig,
     db::DbPool,
-    jobs::leader_lock::{self, LeadershipOutcome, keys},
+    jobs::{
+        JobScheduler,
+        leader_lock::{self, LeadershipOutcome, keys},
+    },
+    models::JobTrigger,
     observability::metrics,
     services::{FileStorage, FileStorageError},
 };
@@ -28,6 +33,8 @@ use crate::{
 /// Results from a single cleanup run.
 #[derive(Debug, Default)]
 pub struct CleanupRunResult {
+    /// Number of vector stores marked expired.
+    pub stores_expired: u64,
     /// Number of vector stores hard-deleted.
     pub stores_deleted: u64,
     /// Number of vector store files (file links) hard-deleted.
@@ -54,13 +61,15 @@ impl CleanupRunResult {
 
 /// Starts the vector store cleanup worker as a background task.
 ///
-/// The worker runs in a loop, cleaning up soft-deleted stores at the configured interval.
-/// It will run indefinitely until the task is cancelled.
+/// The worker runs in a loop, cleaning up soft-deleted stores at the configured interval
+/// or when triggered through the jobs admin API. It will run indefinitely until the
+/// task is cancelled.
 pub async fn start_vector_store_cleanup_worker(
     db: Arc<DbPool>,
     vector_store: Option<Arc<dyn VectorBackend>>,
     file_storage: Option<Arc<dyn FileStorage>>,
     config: VectorStoreCleanupConfig,
+    scheduler: Arc<JobScheduler>,
 ) {
     if !config.enabled {
         tracing::info!("Vector store cleanup worker disabled by configuration");
@@ -92,9 +101,15 @@ pub async fn start_vector_store_cleanup_worker(
         dry_run_msg
     );
 
-    let interval = config.interval();
+    let job = scheduler.register("vector_store_cleanup", config.interval());
+    let mut trigger = JobTrigger::Scheduled;
 
     loop {
+        if !job.should_run(trigger).await {
+            trigger = job.tick().await;
+            continue;
+        }
+
         // Skip ticks where another replica already holds the cleanup lock —
         // running deletes from two replicas would race on external storage
         // (one replica deletes the file while the other is mid-delete).
@@ -102,13 +117,26 @@ pub async fn start_vector_store_cleanup_worker(
             LeadershipOutcome::Leader(g) => Some(g),
             LeadershipOutcome::NotLeader => {
                 tracing::trace!("vector_store_cleanup: not leader this tick, skipping");
-                tokio::time::sleep(interval).await;
+                trigger = job.tick().await;
                 continue;
             }
             LeadershipOutcome::NoCoordination => None,
         };
 
-        match run_cleanup(&db, &vector_store, file_storage.as_ref(), &config).await {
+        let run = run_cleanup(&db, &vector_store, file_storage.as_ref(), &config);
+        let result = job
+            .run(trigger, run, |result| {
+                Some(format!(
+                    "{} stores, {} vector store files and {} files deleted, {} bytes freed{}",
+                    result.stores_deleted,
+                    result.vector_store_files_deleted,
+                    result.files_deleted,
+                    result.storage_bytes_freed,
+                    dry_run_msg
+                ))
+            })
+            .await;
+        match result {
             Ok(result) => {
                 if result.has_deletions() {
                     tracing::info!(
@@ -132,7 +160,7 @@ pub async fn start_vector_store_cleanup_worker(
             }
         }
 
-        tokio::time::sleep(interval).await;
+        trigger = job.tick().await;
     }
 }
 
@@ -150,6 +178,21 @@ async fn run_cleanup(
     let cutoff = Utc::now() - Duration::seconds(config.cleanup_delay_secs as i64);
     let max_duration = config.max_duration();
 
+    // ==================== Phase 0: Expire stores past their expires_at ====================
+    // Expired stores keep their data (they can still be listed and deleted)
+    // but are rejected for search and new files, matching OpenAI.
+    if config.dry_run {
+        tracing::debug!("DRY RUN: skipping vector store expiration");
+    } else {
+        result.stores_expired = db.vector_stores().expire_vector_stores(Utc::now()).await?;
+        if result.stores_expired > 0 {
+            tracing::info!(
+                stores = result.stores_expired,
+                "Expired vector stores per expires_after policy"
+            );
+        }
+    }
+
     // ==================== Phase 1: Clean up soft-deleted vector store files ====================
     // These are individual file links that were removed from vector stores
     let deleted_vector_store_files = db
@@ -0,0 +1,230 @@
+//! Vector store sync worker.
+//!
+//! On every tick the worker checks each source in
+//! `[features.vector_store_sync]` and runs those whose `schedule_secs` have
+//! elapsed since their last run started. Run history and per-document
+//! versions live in the database, so the schedule survives restarts and
+//! only one replica syncs at a time.
+
+use std::{sync::Arc, time::Instant};
+
+use chrono::{DateTime, Utc};
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    config::{VectorStoreSyncConfig, VectorStoreSyncSource},
+    db::{DbPool, DbResult},
+    jobs::leader_lock::{self, LeadershipOutcome, keys},
+    models::{VectorStoreSync, VectorStoreSyncStatus},
+    observability::metrics,
+    services::SourceSyncService,
+};
+
+/// Results from a single scheduling pass.
+#[derive(Debug, Default)]
+pub struct VectorStoreSyncResult {
+    /// Sources synced this pass.
+    pub synced: u64,
+    /// Sources whose run failed or partially failed.
+    pub failed: u64,
+    /// Duration of the pass in milliseconds.
+    pub duration_ms: u64,
+}
+
+/// Starts the vector store sync worker as a background task.
+pub async fn start_vector_store_sync_worker(
+    service: SourceSyncService,
+    db: Arc<DbPool>,
+    config: VectorStoreSyncConfig,
+    shutdown: CancellationToken,
+) {
+    if !config.enabled || config.sources.is_empty() {
+        tracing::info!("Vector store sync worker disabled by configuration");
+        return;
+    }
+
+    tracing::info!(
+        interval_secs = config.interval_secs,
+        sources = config.sources.len(),
+        "Starting vector store sync worker"
+    );
+
+    let interval = config.interval();
+
+    loop {
+        if shutdown.is_cancelled() {
+            tracing::info!("Vector store sync worker received shutdown signal");
+            return;
+        }
+        // Two replicas syncing the same source would ingest every changed
+        // document twice.
+        let _guard = match leader_lock::try_acquire(&db, keys::VECTOR_STORE_SYNC).await {
+            LeadershipOutcome::Leader(g) => Some(g),
+            LeadershipOutcome::NotLeader => {
+                tracing::trace!("vector_store_sync: not leader this tick, skipping");
+                tokio::select! {
+                    _ = shutdown.cancelled() => return,
+                    _ = tokio::time::sleep(interval) => {}
+                }
+                continue;
+            }
+            LeadershipOutcome::NoCoordination => None,
+        };
+
+        match run_vector_store_sync(&service, &db, &config, &shutdown).await {
+            Ok(result) if result.synced > 0 => {
+                tracing::info!(
+                    synced = result.synced,
+                    failed = result.failed,
+                    duration_ms = result.duration_ms,
+                    "Vector store sync pass complete"
+                );
+            }
+            Ok(_) => {
+                tracing::debug!("Vector store sync pass complete, nothing due");
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Error running vector store sync");
+            }
+        }
+
+        tokio::select! {
+            _ = shutdown.cancelled() => return,
+            _ = tokio::time::sleep(interval) => {}
+        }
+    }
+}
+
+/// Run one scheduling pass: sync every source that is due.
+async fn run_vector_store_sync(
+    service: &SourceSyncService,
+    db: &Arc<DbPool>,
+    config: &VectorStoreSyncConfig,
+    shutdown: &CancellationToken,
+) -> DbResult<VectorStoreSyncResult> {
+    let start = Instant::now();
+    let mut result = VectorStoreSyncResult::default();
+
+    for source in &config.sources {
+        if shutdown.is_cancelled() {
+            break;
+        }
+        let latest = db
+            .vector_store_syncs()
+            .latest_for_source(source.vector_store_id, &source.name)
+            .await?;
+        if !is_due(latest.as_ref(), source, Utc::now()) {
+            continue;
+        }
+
+        let sync = match service.run(source).await {
+            Ok(sync) => sync,
+            Err(e) => {
+                result.failed += 1;
+                tracing::error!(
+                    source = %source.name,
+                    error = %e,
+                    "Failed to record vector store sync"
+                );
+                continue;
+            }
+        };
+        result.synced += 1;
+        metrics::record_vector_store_sync(source.connector.as_str(), sync.status.as_str());
+
+        if sync.status == VectorStoreSyncStatus::Succeeded {
+            tracing::info!(
+                source = %source.name,
+                vector_store_id = %source.vector_store_id,
+                added = sync.files_added,
+                updated = sync.files_updated,
+                deleted = sync.files_deleted,
+                unchanged = sync.files_unchanged,
+                "Vector store sync succeeded"
+            );
+        } else {
+            result.failed += 1;
+            tracing::warn!(
+                source = %source.name,
+                vector_store_id = %source.vector_store_id,
+                status = sync.status.as_str(),
+                failed = sync.files_failed,
+                error = sync.error.as_deref().unwrap_or_default(),
+                "Vector store sync did not fully succeed"
+            );
+        }
+    }
+
+    result.duration_ms = start.elapsed().as_millis() as u64;
+    Ok(result)
+}
+
+/// A source is due when it has never run, or its last run started at least
+/// `schedule_secs` ago. Failed runs wait for the next scheduled slot rather
+/// than retrying every tick.
+fn is_due(
+    latest: Option<&VectorStoreSync>,
+    source: &VectorStoreSyncSource,
+    now: DateTime<Utc>,
+) -> bool {
+    let Some(latest) = latest else {
+        return true;
+    };
+    let schedule = chrono::Duration::seconds(source.schedule_secs.min(i64::MAX as u64) as i64);
+    now - latest.started_at >= schedule
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::config::SyncConnector;
+
+    fn source(schedule_secs: u64) -> VectorStoreSyncSource {
+        VectorStoreSyncSource {
+            name: "docs".to_string(),
+            vector_store_id: Uuid::new_v4(),
+            schedule_secs,
+            extensions: vec![],
+            connector: SyncConnector::Git {
+                url: "https://example.com/docs.git".to_string(),
+                branch: None,
+                path: None,
+            },
+        }
+    }
+
+    fn sync_started(started_at: DateTime<Utc>) -> VectorStoreSync {
+        VectorStoreSync {
+            id: Uuid::new_v4(),
+            vector_store_id: Uuid::new_v4(),
+            source_name: "docs".to_string(),
+            connector: "git".to_string(),
+            status: VectorStoreSyncStatus::Failed,
+            files_added: 0,
+            files_updated: 0,
+            files_deleted: 0,
+            files_unchanged: 0,
+            files_failed: 0,
+            error: None,
+            started_at,
+            finished_at: None,
+        }
+    }
+
+    #[test]
+    fn test_is_due_without_previous_run() {
+        assert!(is_due(None, &source(3600), Utc::now()));
+    }
+
+    #[test]
+    fn test_is_due_waits_for_schedule() {
+        let now = Utc::now();
+        let recent = sync_started(now - chrono::Duration::minutes(30));
+        assert!(!is_due(Some(&recent), &source(3600), now));
+
+        let old = sync_started(now - chrono::Duration::minutes(60));
+        assert!(is_due(Some(&old), &source(3600), now));
+    }
+}
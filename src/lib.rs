@@ -28,11 +28,15 @@ pub mod routing;
 pub mod runtimes;
 #[cfg(feature = "sso")]
 pub mod scim;
+#[cfg(feature = "utoipa")]
+pub mod sdk;
 pub mod secrets;
 pub mod services;
 pub mod streaming;
+pub mod transforms;
 pub mod usage_buffer;
 pub mod usage_sink;
+pub mod usage_wal;
 pub mod validation;
 #[cfg(feature = "wizard")]
 pub mod wizard;
@@ -19,7 +19,7 @@ use std::net::IpAddr;
 #[cfg(feature = "server")]
 use axum::extract::ConnectInfo;
 use axum::{
-    extract::{Request, State},
+    extract::{OriginalUri, Request, State},
     middleware::Next,
     response::Response,
 };
@@ -29,7 +29,14 @@ use uuid::Uuid;
 use crate::{
     AppState,
     auth::{AuthError, AuthenticatedRequest, Identity, IdentityKind},
-    middleware::{AdminAuth, ClientInfo, RequestId},
+    middleware::{
+        AdminAuth, ClientInfo, RequestId,
+        util::{
+            scope::required_scope,
+            step_up::{self, TotpAttempt},
+        },
+    },
+    models::RequiredScope,
     observability::metrics,
     services::audit_logs::{AuthEventParams, auth_events},
 };
@@ -70,6 +77,17 @@ pub async fn admin_auth_middleware(
             .map(|s| s.to_string()),
     };
 
+    // Scope an API key needs for this request. Admin routes are nested, so
+    // the full path comes from `OriginalUri`.
+    let path = req
+        .extensions()
+        .get::<OriginalUri>()
+        .map_or_else(|| req.uri().path(), |uri| uri.path());
+    let scope = required_scope(req.method(), path);
+    let step_up_config = &state.config.auth.step_up;
+    let needs_step_up = step_up_config.enabled
+        && step_up::requires_recent_auth(req.method(), path, &step_up_config.routes);
+
     // Try to authenticate via Proxy auth or OIDC
     let identity = match try_admin_auth(
         &headers,
@@ -77,6 +95,7 @@ pub async fn admin_auth_middleware(
         connecting_ip,
         &state,
         &client_info,
+        scope.as_ref(),
     )
     .await
     {
@@ -136,6 +155,15 @@ pub async fn admin_auth_middleware(
         }
     }
 
+    if needs_step_up {
+        enforce_step_up(&state, &identity, cookies.as_ref(), &headers, &client_info).await?;
+    }
+
+    #[cfg(feature = "webauthn")]
+    if state.config.auth.admin.passkeys == crate::config::PasskeyPolicy::Required {
+        enforce_passkey(&state, &identity, cookies.as_ref()).await?;
+    }
+
     // Add identity and client info to request extensions
     let auth = AuthenticatedRequest::new(IdentityKind::Identity(identity.clone()));
     req.extensions_mut().insert(auth);
@@ -145,6 +173,170 @@ pub async fn admin_auth_middleware(
     Ok(next.run(req).await)
 }
 
+/// Require step-up authentication for a protected admin route.
+///
+/// Satisfied by any of, in order:
+/// - an SSO session (the request's cookie) created within `max_auth_age_secs`
+/// - a TOTP verification remembered in the cache
+/// - a valid code in the `X-Step-Up-TOTP` header
+///
+/// Bootstrap and emergency credentials are exempt: they are already
+/// break-glass secrets and have no user to enroll an authenticator.
+async fn enforce_step_up(
+    state: &AppState,
+    identity: &Identity,
+    cookies: Option<&Cookies>,
+    headers: &axum::http::HeaderMap,
+    client_info: &ClientInfo,
+) -> Result<(), AuthError> {
+    if identity
+        .roles
+        .iter()
+        .any(|r| r == BOOTSTRAP_ROLE || r == EMERGENCY_ADMIN_ROLE)
+    {
+        return Ok(());
+    }
+
+    #[cfg(feature = "sso")]
+    {
+        let max_age = state.config.auth.step_up.max_auth_age_secs;
+        if max_age > 0
+            && let Some(signed_in_at) =
+                session_signed_in_at(cookies, state, &identity.external_id).await
+            && chrono::Utc::now() - signed_in_at <= chrono::Duration::seconds(max_age as i64)
+        {
+            return Ok(());
+        }
+    }
+    #[cfg(not(feature = "sso"))]
+    let _ = cookies;
+
+    // TOTP is tied to an internal user record
+    let Some(user_id) = identity.user_id else {
+        return Err(AuthError::StepUpRequired {
+            totp_enrolled: false,
+        });
+    };
+
+    if let Some(cache) = &state.cache
+        && step_up::verified_until(cache.as_ref(), user_id)
+            .await
+            .is_some()
+    {
+        return Ok(());
+    }
+
+    let Some(services) = &state.services else {
+        return Err(AuthError::StepUpRequired {
+            totp_enrolled: false,
+        });
+    };
+
+    if let Some(code) = headers
+        .get(step_up::STEP_UP_TOTP_HEADER)
+        .and_then(|v| v.to_str().ok())
+    {
+        let attempt = step_up::attempt_totp(state, services, identity, user_id, code, client_info)
+            .await
+            .map_err(|e| AuthError::Internal(e.to_string()))?;
+        return match attempt {
+            TotpAttempt::Verified(_) => Ok(()),
+            TotpAttempt::Invalid => Err(AuthError::StepUpRequired {
+                totp_enrolled: true,
+            }),
+            TotpAttempt::NotEnrolled => Err(AuthError::StepUpRequired {
+                totp_enrolled: false,
+            }),
+            TotpAttempt::LockedOut => Err(AuthError::Forbidden(
+                "Too many failed step-up attempts; try again later".to_string(),
+            )),
+        };
+    }
+
+    let totp_enrolled = services
+        .step_up
+        .is_enrolled(user_id)
+        .await
+        .map_err(|e| AuthError::Internal(e.to_string()))?;
+    Err(AuthError::StepUpRequired { totp_enrolled })
+}
+
+/// When the SSO session in the request's cookie was created, if it belongs
+/// to `external_id`. Sessions are created at sign-in and never extended, so
+/// this is the time of the last interactive authentication.
+#[cfg(feature = "sso")]
+async fn session_signed_in_at(
+    cookies: Option<&Cookies>,
+    state: &AppState,
+    external_id: &str,
+) -> Option<chrono::DateTime<chrono::Utc>> {
+    current_session(cookies, state, external_id)
+        .await
+        .map(|s| s.created_at)
+}
+
+/// The SSO session in the request's cookie, if it belongs to `external_id`.
+#[cfg(feature = "sso")]
+async fn current_session(
+    cookies: Option<&Cookies>,
+    state: &AppState,
+    external_id: &str,
+) -> Option<crate::auth::session_store::OidcSession> {
+    let cookie_name = state
+        .config
+        .auth
+        .session_config_or_default()
+        .cookie_name
+        .clone();
+    let session_id: Uuid = cookies?.get(&cookie_name)?.value().parse().ok()?;
+
+    let session = match &state.oidc_registry {
+        Some(registry) => registry
+            .session_store()
+            .get_session(session_id)
+            .await
+            .ok()
+            .flatten(),
+        None => None,
+    };
+    #[cfg(feature = "saml")]
+    let session = match (session, &state.saml_registry) {
+        (None, Some(registry)) => registry.get_session(session_id).await.ok().flatten(),
+        (session, _) => session,
+    };
+
+    session.filter(|s| s.external_id == external_id)
+}
+
+/// Require SSO sessions to be verified with a passkey when
+/// `[auth.admin] passkeys = "required"`.
+///
+/// Only applies to cookie sessions: bearer tokens, proxy headers and
+/// bootstrap/emergency credentials have no session to verify.
+#[cfg(feature = "webauthn")]
+async fn enforce_passkey(
+    state: &AppState,
+    identity: &Identity,
+    cookies: Option<&Cookies>,
+) -> Result<(), AuthError> {
+    let Some(session) = current_session(cookies, state, &identity.external_id).await else {
+        return Ok(());
+    };
+    if session.passkey_verified_at.is_some() {
+        return Ok(());
+    }
+
+    let registered = match (&state.services, identity.user_id) {
+        (Some(services), Some(user_id)) => services
+            .passkeys
+            .has_any(user_id)
+            .await
+            .map_err(|e| AuthError::Internal(e.to_string()))?,
+        _ => false,
+    };
+    Err(AuthError::PasskeyRequired { registered })
+}
+
 /// Check if the request is an XHR/API request (as opposed to a browser navigation).
 /// XHR requests should receive 401 responses, not redirects, to avoid CORS issues.
 fn is_xhr_request(headers: &axum::http::HeaderMap) -> bool {
@@ -669,6 +861,7 @@ async fn try_admin_auth(
     connecting_ip: Option<IpAddr>,
     state: &AppState,
     client_info: &ClientInfo,
+    scope: Option<&RequiredScope>,
 ) -> Result<Identity, AuthError> {
     #[cfg(not(feature = "sso"))]
     let _ = &cookies;
@@ -686,7 +879,7 @@ async fn try_admin_auth(
 
     // Try API key (for ApiKey mode — admin panel sends key via Authorization/X-API-Key)
     if matches!(state.config.auth.mode, crate::config::AuthMode::ApiKey)
-        && let Some(identity) = try_api_key_admin_auth(headers, state).await?
+        && let Some(identity) = try_api_key_admin_auth(headers, scope, state).await?
     {
         return Ok(identity);
     }
@@ -738,10 +931,12 @@ async fn try_admin_auth(
 /// Try to authenticate via API key for admin access (ApiKey mode).
 ///
 /// Validates the API key from `Authorization: Bearer` or `X-API-Key` headers
-/// using the same logic as API endpoint authentication. Builds an `Identity`
-/// from the key owner's information (user, service account, or org).
+/// using the same logic as API endpoint authentication, then checks the key's
+/// scopes against `scope`. Builds an `Identity` from the key owner's
+/// information (user, service account, or org).
 async fn try_api_key_admin_auth(
     headers: &axum::http::HeaderMap,
+    scope: Option<&RequiredScope>,
     state: &AppState,
 ) -> Result<Option<Identity>, AuthError> {
     let api_key_auth = match super::api::try_api_key_auth(headers, state).await? {
@@ -749,6 +944,21 @@ async fn try_api_key_admin_auth(
         None => return Ok(None),
     };
 
+    if let Some(required) = scope
+        && !api_key_auth.key.has_scope(required)
+    {
+        tracing::warn!(
+            api_key_id = %api_key_auth.key.id,
+            required_scope = %required,
+            available_scopes = ?api_key_auth.key.scopes,
+            "API key lacks required admin scope"
+        );
+        return Err(AuthError::InsufficientScope {
+            required: required.to_string(),
+            available: api_key_auth.key.scopes.clone().unwrap_or_default(),
+        });
+    }
+
     // Build Identity from the API key's owner information.
     // For user-owned keys, look up the user's memberships from the database.
     // For service-account-owned keys, use the SA roles.
@@ -1820,6 +2030,7 @@ async fn jit_provision_org_scoped(
 
     let mut current_org_ids = Vec::new();
     let mut current_team_ids = Vec::new();
+    let mut current_project_ids = Vec::new();
 
     if let Some(user_id) = user_id {
         current_org_ids.push(org_id);
@@ -2052,13 +2263,26 @@ async fn jit_provision_org_scoped(
             }
         }
 
-        // Step 6: Sync memberships if enabled
+        // Step 6: Apply claim-based role rules
+        apply_role_rules(
+            db,
+            user_id,
+            org_id,
+            &session.claims,
+            &mut current_team_ids,
+            &mut current_project_ids,
+            client_info,
+        )
+        .await;
+
+        // Step 7: Sync memberships if enabled
         if provisioning.sync_memberships_on_login {
             sync_memberships(
                 db,
                 user_id,
                 &current_org_ids,
                 &current_team_ids,
+                &current_project_ids,
                 client_info,
             )
             .await;
@@ -2067,7 +2291,125 @@ async fn jit_provision_org_scoped(
 
     let org_ids: Vec<String> = current_org_ids.iter().map(|id| id.to_string()).collect();
     let team_ids: Vec<String> = current_team_ids.iter().map(|id| id.to_string()).collect();
-    Ok((user_id, org_ids, team_ids, Vec::new()))
+    let project_ids: Vec<String> = current_project_ids
+        .iter()
+        .map(|id| id.to_string())
+        .collect();
+    Ok((user_id, org_ids, team_ids, project_ids))
+}
+
+/// Grant team and project memberships from the org's claim-based role rules.
+///
+/// Targets already granted earlier in provisioning (default team, group
+/// mappings) are left alone. Granted targets are appended to
+/// `current_team_ids` / `current_project_ids` so membership sync keeps them.
+#[cfg(feature = "sso")]
+async fn apply_role_rules(
+    db: &crate::db::DbPool,
+    user_id: Uuid,
+    org_id: Uuid,
+    claims: &std::collections::HashMap<String, serde_json::Value>,
+    current_team_ids: &mut Vec<Uuid>,
+    current_project_ids: &mut Vec<Uuid>,
+    client_info: &ClientInfo,
+) {
+    use crate::{
+        db::DbError,
+        models::{
+            AddTeamMember, AuditActorType, CreateAuditLog, MembershipSource, RoleRuleTarget,
+            resolve_role_rules,
+        },
+        observability::metrics,
+    };
+
+    if claims.is_empty() {
+        return;
+    }
+
+    let rules = match db.org_sso_configs().get_role_rules(org_id).await {
+        Ok(rules) => rules,
+        Err(e) => {
+            tracing::warn!(error = %e, org_id = %org_id, "Failed to load SSO role rules");
+            return;
+        }
+    };
+
+    for resolved in resolve_role_rules(&rules, claims) {
+        let (result, resource_type, target_id) = match resolved.target {
+            RoleRuleTarget::Team(team_id) => {
+                if current_team_ids.contains(&team_id) {
+                    continue;
+                }
+                current_team_ids.push(team_id);
+                let add_member = AddTeamMember {
+                    user_id,
+                    role: resolved.role.clone(),
+                    source: MembershipSource::Jit,
+                };
+                let result = db.teams().add_member(team_id, add_member).await.map(|_| ());
+                (result, "team_membership", team_id)
+            }
+            RoleRuleTarget::Project(project_id) => {
+                if current_project_ids.contains(&project_id) {
+                    continue;
+                }
+                current_project_ids.push(project_id);
+                let result = db
+                    .users()
+                    .add_to_project(user_id, project_id, &resolved.role, MembershipSource::Jit)
+                    .await;
+                (result, "project_membership", project_id)
+            }
+        };
+
+        match result {
+            Ok(()) => {
+                tracing::info!(
+                    user_id = %user_id,
+                    target = %target_id,
+                    role = %resolved.role,
+                    rule = %resolved.rule,
+                    "JIT granted membership via role rule"
+                );
+                metrics::record_jit_provision(resource_type, "created");
+
+                let _ = db
+                    .audit_logs()
+                    .create(CreateAuditLog {
+                        actor_type: AuditActorType::System,
+                        actor_id: None,
+                        action: format!("{resource_type}.jit_role_rule"),
+                        resource_type: resource_type.to_string(),
+                        resource_id: user_id,
+                        org_id: Some(org_id),
+                        project_id: match resolved.target {
+                            RoleRuleTarget::Project(id) => Some(id),
+                            RoleRuleTarget::Team(_) => None,
+                        },
+                        details: serde_json::json!({
+                            "user_id": user_id,
+                            "target": resolved.target,
+                            "role": resolved.role,
+                            "rule": resolved.rule,
+                        }),
+                        ip_address: client_info.ip_address.clone(),
+                        user_agent: client_info.user_agent.clone(),
+                    })
+                    .await;
+            }
+            Err(DbError::Conflict(_)) => {
+                // Already a member, ignore
+            }
+            Err(e) => {
+                tracing::warn!(
+                    error = %e,
+                    target = %target_id,
+                    rule = %resolved.rule,
+                    "Failed to grant membership via role rule"
+                );
+            }
+        }
+    }
 }
 
 /// Get or create a user by external_id, handling race conditions.
@@ -2153,7 +2495,7 @@ async fn get_or_create_user(
     }
 }
 
-/// Sync user's org/team memberships by removing JIT-created memberships not in current groups.
+/// Sync user's org/team/project memberships by removing JIT-created memberships not in current groups.
 ///
 /// IMPORTANT: This function ONLY removes memberships with `source = 'jit'`. Memberships created
 /// manually (via admin API/UI) or via SCIM provisioning are preserved. This ensures that:
@@ -2165,6 +2507,7 @@ async fn sync_memberships(
     user_id: Uuid,
     current_org_ids: &[Uuid],
     current_team_ids: &[Uuid],
+    current_project_ids: &[Uuid],
     client_info: &ClientInfo,
 ) {
     use crate::{
@@ -2269,6 +2612,52 @@ async fn sync_memberships(
             );
         }
     }
+
+    // Project memberships only come from role rules, so JIT ones no longer
+    // granted by a rule are removed.
+    match db
+        .users()
+        .remove_project_memberships_by_source(user_id, MembershipSource::Jit, current_project_ids)
+        .await
+    {
+        Ok(count) if count > 0 => {
+            tracing::info!(
+                user_id = %user_id,
+                removed_count = count,
+                "JIT removed user from projects (sync - JIT memberships only)"
+            );
+            metrics::record_jit_provisions("project_membership", "removed", count);
+
+            let _ = db
+                .audit_logs()
+                .create(CreateAuditLog {
+                    actor_type: AuditActorType::System,
+                    actor_id: None,
+                    action: "project_membership.jit_sync_removed".to_string(),
+                    resource_type: "project_membership".to_string(),
+                    resource_id: user_id,
+                    org_id: None,
+                    project_id: None,
+                    details: serde_json::json!({
+                        "user_id": user_id,
+                        "removed_count": count,
+                        "reason": "no_matching_role_rule",
+                        "source": "jit",
+                    }),
+                    ip_address: client_info.ip_address.clone(),
+                    user_agent: client_info.user_agent.clone(),
+                })
+                .await;
+        }
+        Ok(_) => {}
+        Err(e) => {
+            tracing::warn!(
+                error = %e,
+                user_id = %user_id,
+                "Failed to remove JIT project memberships during sync"
+            );
+        }
+    }
 }
 
 /// Resolve an organization by ID (UUID) or slug.
@@ -2455,8 +2844,18 @@ mod tests {
             dlq: None,
             pricing: Arc::new(crate::pricing::PricingConfig::default()),
             circuit_breakers: crate::providers::CircuitBreakerRegistry::new(),
+            fair_queues: crate::providers::FairQueueRegistry::new(),
+            adaptive_limiters: crate::providers::AdaptiveConcurrencyRegistry::new(),
+            provider_settings: crate::providers::ProviderSettingsRegistry::new(
+                crate::providers::CircuitBreakerRegistry::new(),
+                crate::providers::FairQueueRegistry::new(),
+            ),
+            request_smoother: None,
+            token_buckets: None,
             provider_health: crate::jobs::ProviderHealthStateRegistry::new(),
+            job_scheduler: Arc::new(crate::jobs::JobScheduler::new(None)),
             task_tracker: TaskTracker::new(),
+            drain: Arc::new(crate::middleware::DrainState::new()),
             usage_drain: {
                 let tracker = TaskTracker::new();
                 crate::streaming::UsageDrainHandle::spawn(&tracker, 16)
@@ -2466,12 +2865,21 @@ mod tests {
             #[cfg(feature = "saml")]
             saml_registry: None,
             gateway_jwt_registry: None,
+            bearer_jwt: None,
             policy_registry: None,
+            request_policies: None,
             usage_buffer: None,
             response_cache: None,
+            embeddings_cache: None,
             semantic_cache: None,
             input_guardrails: None,
             output_guardrails: None,
+            transforms: None,
+            shadow_traffic: None,
+            idempotency: None,
+            token_counter: None,
+            embedding_batcher: None,
+            email_notifier: None,
             event_bus: Arc::new(crate::events::EventBus::new()),
             file_search_service: None,
             shell_runtime: None,
@@ -2775,8 +3183,18 @@ mod tests {
             dlq: None,
             pricing: Arc::new(crate::pricing::PricingConfig::default()),
             circuit_breakers: crate::providers::CircuitBreakerRegistry::new(),
+            fair_queues: crate::providers::FairQueueRegistry::new(),
+            adaptive_limiters: crate::providers::AdaptiveConcurrencyRegistry::new(),
+            provider_settings: crate::providers::ProviderSettingsRegistry::new(
+                crate::providers::CircuitBreakerRegistry::new(),
+                crate::providers::FairQueueRegistry::new(),
+            ),
+            request_smoother: None,
+            token_buckets: None,
             provider_health: crate::jobs::ProviderHealthStateRegistry::new(),
+            job_scheduler: Arc::new(crate::jobs::JobScheduler::new(None)),
             task_tracker: TaskTracker::new(),
+            drain: Arc::new(crate::middleware::DrainState::new()),
             usage_drain: {
                 let tracker = TaskTracker::new();
                 crate::streaming::UsageDrainHandle::spawn(&tracker, 16)
@@ -2786,12 +3204,21 @@ mod tests {
             #[cfg(feature = "saml")]
             saml_registry: None,
             gateway_jwt_registry: None,
+            bearer_jwt: None,
             policy_registry: None,
+            request_policies: None,
             usage_buffer: None,
             response_cache: None,
+            embeddings_cache: None,
             semantic_cache: None,
             input_guardrails: None,
             output_guardrails: None,
+            transforms: None,
+            shadow_traffic: None,
+            idempotency: None,
+            token_counter: None,
+            embedding_batcher: None,
+            email_notifier: None,
             event_bus: Arc::new(crate::events::EventBus::new()),
             file_search_service: None,
             shell_runtime: None,
@@ -3148,4 +3575,101 @@ mod tests {
         let result = extract_emergency_key(&headers);
         assert!(result.is_none());
     }
+
+    // ========== Step-up enforcement ==========
+
+    fn step_up_identity(user_id: Option<Uuid>, roles: Vec<String>) -> Identity {
+        Identity {
+            external_id: "alice".to_string(),
+            email: None,
+            name: None,
+            user_id,
+            roles,
+            idp_groups: vec![],
+            org_ids: vec![],
+            team_ids: vec![],
+            project_ids: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_step_up_exempts_break_glass_roles() {
+        let state = create_test_state("X-Forwarded-User", TrustedProxiesConfig::default());
+        for role in [BOOTSTRAP_ROLE, EMERGENCY_ADMIN_ROLE] {
+            let identity = step_up_identity(None, vec![role.to_string()]);
+            let result = enforce_step_up(
+                &state,
+                &identity,
+                None,
+                &make_headers(vec![]),
+                &ClientInfo::default(),
+            )
+            .await;
+            assert!(result.is_ok(), "{role} should be exempt");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_step_up_requires_user_record() {
+        let state = create_test_state("X-Forwarded-User", TrustedProxiesConfig::default());
+        let identity = step_up_identity(None, vec!["super_admin".to_string()]);
+        let result = enforce_step_up(
+            &state,
+            &identity,
+            None,
+            &make_headers(vec![("X-Step-Up-TOTP", "123456")]),
+            &ClientInfo::default(),
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(AuthError::StepUpRequired {
+                totp_enrolled: false
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_step_up_accepts_cached_verification() {
+        let mut state = create_test_state("X-Forwarded-User", TrustedProxiesConfig::default());
+        let cache: Arc<dyn crate::cache::Cache> = Arc::new(crate::cache::MemoryCache::new(
+            &crate::config::MemoryCacheConfig::default(),
+        ));
+        state.cache = Some(cache.clone());
+        let user_id = Uuid::new_v4();
+        let identity = step_up_identity(Some(user_id), vec![]);
+
+        let result = enforce_step_up(
+            &state,
+            &identity,
+            None,
+            &make_headers(vec![]),
+            &ClientInfo::default(),
+        )
+        .await;
+        assert!(matches!(result, Err(AuthError::StepUpRequired { .. })));
+
+        step_up::mark_verified(cache.as_ref(), user_id, 600).await;
+        let result = enforce_step_up(
+            &state,
+            &identity,
+            None,
+            &make_headers(vec![]),
+            &ClientInfo::default(),
+        )
+        .await;
+        assert!(result.is_ok());
+
+        // Another user's verification doesn't carry over
+        let other = step_up_identity(Some(Uuid::new_v4()), vec![]);
+        let result = enforce_step_up(
+            &state,
+            &other,
+            None,
+            &make_headers(vec![]),
+            &ClientInfo::default(),
+        )
+        .await;
+        assert!(result.is_err());
+    }
 }
@@ -3,7 +3,9 @@ use std::{net::IpAddr, sync::Arc, time::Duration};
 #[cfg(feature = "server")]
 use axum::extract::ConnectInfo;
 use axum::{
+    body::Body,
     extract::{Request, State},
+    http::StatusCode,
     middleware::Next,
     response::{IntoResponse, Response},
 };
@@ -15,26 +17,46 @@ use super::rate_limit::{
 };
 use crate::{
     AppState,
-    auth::{ApiKeyAuth, AuthError, AuthenticatedRequest, Identity, IdentityKind},
-    cache::{BudgetCheckParams, Cache, CacheKeys, RateLimitCheckParams, RateLimitResult},
+    auth::{
+        ApiKeyAuth, AuthError, AuthenticatedRequest, ClientCertAuth, ClientCertificate, Identity,
+        IdentityKind,
+    },
+    cache::{
+        BudgetCheckParams, Cache, CacheKeys, RateLimitCheckParams, RateLimitResult,
+        TokenBucketLimiter,
+    },
+    config::{AttributionHeadersConfig, TransformEndpoint},
     events::{BudgetType, ServerEvent},
     middleware::{
         RequestId,
         util::{
+            api_key::{FoundApiKey, find_api_key},
             budget::{BudgetCheckResult, BudgetError, adjust_budget_reservation},
-            scope::required_scope_for_path,
+            scope::required_scope,
             usage::{UsageTracker, extract_full_usage_from_response, tracker_from_headers},
         },
     },
-    models::{AuditActorType, BudgetPeriod, CreateAuditLog, has_valid_prefix, hash_api_key},
-    observability::metrics,
+    models::{
+        ApiKeyHasher, ApiKeyScope, AuditActorType, BudgetPeriod, CreateAuditLog, RequiredScope,
+        ScopeAccess, has_valid_prefix,
+    },
+    observability::{metrics, slo},
+    openapi::ErrorResponse,
+    providers::{
+        error::{ERROR_CATEGORY_HEADER, ErrorCategory},
+        retry::ProviderAttempts,
+    },
+    services::{
+        notifications::{SpendAlert, send_spend_alert},
+        token_counter::LimitEstimate,
+    },
 };
 
 /// Input parameters for combined limit checking
 pub struct LimitsCheckInput<'a> {
     pub cache: &'a Arc<dyn Cache>,
     pub api_key: &'a ApiKeyAuth,
-    pub estimated_cost_cents: i64,
+    pub estimated_cost_microcents: i64,
     pub tpm_limit: u32,
     pub tpd_limit: Option<u32>,
     pub estimated_tokens: i64,
@@ -42,6 +64,9 @@ pub struct LimitsCheckInput<'a> {
     pub rpd_limit: Option<u32>,
     /// Warning threshold as a percentage (0.0-1.0)
     pub budget_warning_threshold: f64,
+    /// Token buckets for the request-per-minute limit, if configured in
+    /// place of the fixed window.
+    pub token_buckets: Option<&'a TokenBucketLimiter>,
 }
 
 /// Context for async usage tracking
@@ -76,6 +101,8 @@ pub struct BudgetExceededEvent<'a> {
 pub struct BudgetWarningEvent<'a> {
     pub state: &'a AppState,
     pub api_key_id: uuid::Uuid,
+    pub api_key_name: &'a str,
+    pub api_key_prefix: &'a str,
     pub org_id: Option<uuid::Uuid>,
     pub project_id: Option<uuid::Uuid>,
     pub spend_percentage: f64,
@@ -137,7 +164,8 @@ impl IntoResponse for CombinedLimitError {
 /// - Budget check (if configured on API key)
 /// - Token per-minute limit
 /// - Token per-day limit (if configured)
-/// - Request per-minute limit
+/// - Request per-minute limit (unless token buckets are configured, which are
+///   checked separately once the batched limits pass)
 /// - Request per-day limit (if configured)
 ///
 /// Returns CombinedLimitResult with reservation info for later adjustment.
@@ -147,13 +175,14 @@ async fn check_all_limits_batch(
     let LimitsCheckInput {
         cache,
         api_key,
-        estimated_cost_cents,
+        estimated_cost_microcents,
         tpm_limit,
         tpd_limit,
         estimated_tokens,
         rpm_limit,
         rpd_limit,
         budget_warning_threshold,
+        token_buckets,
     } = input;
     // Prepare all the budget check parameters (for budget + token limits)
     let mut budget_checks = Vec::with_capacity(3);
@@ -167,7 +196,6 @@ async fn check_all_limits_batch(
         (api_key.key.budget_limit_cents, api_key.key.budget_period)
     {
         // Use saturating_mul to prevent overflow (cents * 10_000 = microcents)
-        let estimated_cost_microcents = estimated_cost_cents.saturating_mul(10_000);
         let limit_microcents = limit_cents.saturating_mul(10_000);
         let cache_key = CacheKeys::spend(api_key_id, period);
         // Use fixed full-period TTL to prevent race conditions with long-running requests
@@ -217,11 +245,13 @@ async fn check_all_limits_batch(
     };
 
     // Request per-minute rate limit
-    rate_limit_checks.push(RateLimitCheckParams {
-        key: CacheKeys::rate_limit(api_key_id, "minute"),
-        limit: rpm_limit,
-        window_secs: 60,
-    });
+    if token_buckets.is_none() {
+        rate_limit_checks.push(RateLimitCheckParams {
+            key: CacheKeys::rate_limit(api_key_id, "minute"),
+            limit: rpm_limit,
+            window_secs: 60,
+        });
+    }
 
     // Request per-day rate limit (if configured)
     let has_rpd = rpd_limit.is_some();
@@ -488,11 +518,23 @@ async fn check_all_limits_batch(
     };
 
     // Process request per-minute rate limit
-    let rpm_result = rate_limit_iter.next().ok_or_else(|| {
-        CombinedLimitError::RateLimit(RateLimitError::Internal(
-            "Missing request per-minute rate limit result".to_string(),
-        ))
-    })?;
+    let rpm_result = match token_buckets {
+        Some(buckets) => {
+            buckets
+                .check(
+                    cache,
+                    &CacheKeys::rate_limit(api_key_id, "bucket"),
+                    rpm_limit,
+                    60,
+                )
+                .await
+        }
+        None => rate_limit_iter.next().ok_or_else(|| {
+            CombinedLimitError::RateLimit(RateLimitError::Internal(
+                "Missing request per-minute rate limit result".to_string(),
+            ))
+        })?,
+    };
 
     if !rpm_result.allowed {
         refund_reservations(
@@ -509,7 +551,7 @@ async fn check_all_limits_batch(
         }
         metrics::record_rate_limit("limited", Some(api_key_id));
         return Err(CombinedLimitError::RateLimit(RateLimitError::Exceeded {
-            limit: rpm_limit,
+            limit: rpm_result.limit,
             current: rpm_result.current,
             window: "minute".to_string(),
             retry_after: rpm_result.reset_secs,
@@ -559,6 +601,58 @@ async fn check_all_limits_batch(
     })
 }
 
+/// Count a data-plane request's prompt to size its limit reservation.
+///
+/// The body is buffered and put back into the returned request. The estimate
+/// is zero for the preflight endpoints, and `None` unless
+/// `[features.token_counting]` is used for limits, and for other endpoints or
+/// bodies that aren't JSON.
+async fn count_for_limits(
+    state: &AppState,
+    req: Request,
+) -> Result<(Request, Option<LimitEstimate>), Response> {
+    // Preflight endpoints never reach a provider, so they reserve nothing
+    // and `/v1/estimate` reads the counters as they'd be for the real request
+    if matches!(
+        req.uri()
+            .path()
+            .strip_prefix("/api")
+            .unwrap_or(req.uri().path()),
+        "/v1/tokenize" | "/v1/estimate"
+    ) {
+        return Ok((
+            req,
+            Some(LimitEstimate {
+                tokens: 0,
+                cost_microcents: Some(0),
+            }),
+        ));
+    }
+    let Some(counter) = state.token_counter.as_ref().filter(|c| c.use_for_limits()) else {
+        return Ok((req, None));
+    };
+    if TransformEndpoint::from_path(req.uri().path()).is_none() {
+        return Ok((req, None));
+    }
+    let (parts, body) = req.into_parts();
+    let bytes = axum::body::to_bytes(body, state.config.server.body_limit_bytes)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::PAYLOAD_TOO_LARGE,
+                axum::Json(ErrorResponse::new(
+                    "payload_too_large",
+                    "Request body too large",
+                )),
+            )
+                .into_response()
+        })?;
+    let estimate = serde_json::from_slice::<serde_json::Value>(&bytes)
+        .ok()
+        .and_then(|body| counter.estimate_limits(state, &body));
+    Ok((Request::from_parts(parts, Body::from(bytes)), estimate))
+}
+
 /// Combined middleware that handles auth, budget checking, and usage tracking
 /// This is applied to all API routes
 pub async fn api_middleware(
@@ -610,10 +704,22 @@ pub async fn api_middleware(
     let has_credentials = headers
         .contains_key(state.config.auth.api_key_config().header_name.as_str())
         || headers.contains_key(axum::http::header::AUTHORIZATION);
-    let auth_result = if !state.config.auth.is_auth_enabled() && !has_credentials {
-        Err(AuthError::MissingCredentials)
-    } else {
-        try_authenticate(&headers, cookies.as_ref(), connecting_ip, &state).await
+    // A verified client certificate (attached by the native TLS listener) is
+    // only used when the request carries no explicit credential headers, so a
+    // workload holding both a cert and an API key can still choose the key.
+    // Unmapped certificates fall through to the configured auth mode.
+    let client_cert = req.extensions().get::<Arc<ClientCertificate>>().cloned();
+    let cert_auth = match &client_cert {
+        Some(cert) if !has_credentials => try_client_cert_auth(cert, &state).await,
+        _ => Ok(None),
+    };
+    let auth_result = match cert_auth {
+        Ok(Some(auth)) => Ok(auth),
+        Err(e) => Err(e),
+        Ok(None) if !state.config.auth.is_auth_enabled() && !has_credentials => {
+            Err(AuthError::MissingCredentials)
+        }
+        Ok(None) => try_authenticate(&headers, cookies.as_ref(), connecting_ip, &state).await,
     };
 
     // Budget reservation (if applicable)
@@ -642,6 +748,8 @@ pub async fn api_middleware(
             IdentityKind::ApiKey(_) => "api_key",
             IdentityKind::Identity(_) => "identity",
             IdentityKind::Both { .. } => "both",
+            IdentityKind::ClientCert(_) => "client_cert",
+            IdentityKind::BearerJwt(_) => "jwt",
         };
         metrics::record_auth_attempt(auth_method, true);
 
@@ -657,8 +765,8 @@ pub async fn api_middleware(
 
         // 2.5. Check API key scopes (if API key auth and path requires a scope)
         if let Some(api_key) = auth.api_key()
-            && let Some(required_scope) = required_scope_for_path(&path)
-            && !api_key.key.has_scope(required_scope)
+            && let Some(required_scope) = required_scope(req.method(), &path)
+            && !api_key.key.has_scope(&required_scope)
         {
             tracing::warn!(
                 request_id = ?request_id,
@@ -716,10 +824,111 @@ pub async fn api_middleware(
             }
         }
 
+        // 2.6.1. Check the organization's network policy, which applies to every
+        // API key attributed to the org on top of the key's own allowlist
+        if let Some(api_key) = auth.api_key()
+            && let Some(org_id) = api_key.org_id
+        {
+            let policy = match org_network_policy(&state, org_id).await {
+                Ok(policy) => policy,
+                Err(e) => return e.into_response(),
+            };
+            if let Some(policy) = policy {
+                let client_ip = super::rate_limit::extract_client_ip(
+                    &req,
+                    &state.config.server.trusted_proxies,
+                );
+                if !client_ip.is_some_and(|ip| policy.is_ip_allowed(ip)) {
+                    let ip = client_ip.map_or_else(|| "unknown".to_string(), |ip| ip.to_string());
+                    tracing::warn!(
+                        request_id = ?request_id,
+                        api_key_id = %api_key.key.id,
+                        %org_id,
+                        client_ip = %ip,
+                        "Request blocked by organization network policy"
+                    );
+                    log_network_policy_denial(&state, api_key, org_id, &ip, req.uri().path());
+                    return AuthError::IPNotAllowed {
+                        ip,
+                        allowlist: policy.ip_allowlist,
+                    }
+                    .into_response();
+                }
+            }
+        }
+
+        // 2.6.2. A key with a recent usage anomaly may be held to a lower rate
+        let anomaly_rpm = match auth.api_key() {
+            Some(api_key) => anomaly_rate_limit(&state, api_key.key.id).await,
+            None => None,
+        };
+
+        // 2.7. Smooth bursts: hold a request that arrives faster than its key's
+        // rate until a token frees up, instead of letting the window check reject it
+        #[cfg(feature = "server")]
+        if let (Some(smoother), Some(api_key)) = (&state.request_smoother, auth.api_key()) {
+            let rpm = api_key
+                .key
+                .rate_limit_rpm
+                .map(|r| r as u32)
+                .unwrap_or(rpm_limit);
+            let rpm = anomaly_rpm.map_or(rpm, |cap| rpm.min(cap));
+            use crate::middleware::util::smoothing::Admission;
+
+            match smoother.admit(api_key.key.id, rpm, std::time::Instant::now()) {
+                Admission::Now => {}
+                Admission::After(delay) => {
+                    metrics::record_rate_limit_smoothing("delayed", delay.as_secs_f64());
+                    tracing::debug!(
+                        request_id = ?request_id,
+                        api_key_id = %api_key.key.id,
+                        delay_ms = delay.as_millis() as u64,
+                        "Delaying burst request"
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                Admission::Rejected { retry_after } => {
+                    metrics::record_rate_limit_smoothing("rejected", 0.0);
+                    metrics::record_rate_limit("limited", api_key_id);
+                    tracing::warn!(
+                        request_id = ?request_id,
+                        api_key_id = %api_key.key.id,
+                        "Burst exceeds smoothing delay"
+                    );
+                    return RateLimitError::Exceeded {
+                        limit: rpm,
+                        current: rpm as i64,
+                        window: "minute".to_string(),
+                        retry_after: retry_after.as_secs_f64().ceil().max(1.0) as u64,
+                    }
+                    .into_response();
+                }
+            }
+        }
+
         // 3. Check all limits (budget + token + request) in a single batched operation
         // This uses Redis pipelining to reduce network round trips (1 RTT instead of 4-5)
         if let (Some(cache), Some(api_key)) = (&state.cache, auth.api_key()) {
-            let estimated_cost_cents = state.config.limits.budgets.estimated_cost_cents;
+            // With token counting, reserve the counted prompt (plus requested
+            // output) instead of the fixed per-request estimates
+            let limit_estimate = match count_for_limits(&state, req).await {
+                Ok((counted, estimate)) => {
+                    req = counted;
+                    estimate
+                }
+                Err(response) => return response,
+            };
+            let estimated_tokens = limit_estimate.map_or(estimated_tokens, |e| e.tokens);
+            let estimated_cost_microcents = limit_estimate
+                .and_then(|e| e.cost_microcents)
+                .unwrap_or_else(|| {
+                    state
+                        .config
+                        .limits
+                        .budgets
+                        .estimated_cost_cents
+                        .saturating_mul(10_000)
+                });
 
             // Use per-key rate limits if configured, otherwise fall back to global defaults
             let effective_rpm = api_key
@@ -727,6 +936,7 @@ pub async fn api_middleware(
                 .rate_limit_rpm
                 .map(|r| r as u32)
                 .unwrap_or(rpm_limit);
+            let effective_rpm = anomaly_rpm.map_or(effective_rpm, |cap| effective_rpm.min(cap));
             let effective_tpm = api_key
                 .key
                 .rate_limit_tpm
@@ -736,13 +946,14 @@ pub async fn api_middleware(
             match check_all_limits_batch(LimitsCheckInput {
                 cache,
                 api_key,
-                estimated_cost_cents,
+                estimated_cost_microcents,
                 tpm_limit: effective_tpm,
                 tpd_limit,
                 estimated_tokens,
                 rpm_limit: effective_rpm,
                 rpd_limit,
                 budget_warning_threshold,
+                token_buckets: state.token_buckets.as_deref(),
             })
             .await
             {
@@ -767,6 +978,8 @@ pub async fn api_middleware(
                         log_budget_warning(BudgetWarningEvent {
                             state: &state,
                             api_key_id: api_key.key.id,
+                            api_key_name: &api_key.key.name,
+                            api_key_prefix: &api_key.key.key_prefix,
                             org_id: api_key.org_id,
                             project_id: api_key.project_id,
                             spend_percentage: warning.spend_percentage,
@@ -857,6 +1070,28 @@ pub async fn api_middleware(
         return AuthError::MissingCredentials.into_response();
     };
 
+    // Lets handlers degrade models for callers nearing their budget
+    if budget_warning.is_some() {
+        req.extensions_mut()
+            .insert(crate::services::model_degradation::BudgetPressure);
+    }
+
+    // Announce the request to admin request tail subscribers
+    let tail_attribution = (state.event_bus.subscriber_count() > 0)
+        .then(|| RequestTailAttribution::new(&state, auth_clone.as_ref()));
+    if let Some(attribution) = &tail_attribution {
+        state.event_bus.publish(ServerEvent::RequestStarted {
+            request_id: request_id.clone().unwrap_or_default(),
+            timestamp: Utc::now(),
+            method: method.clone(),
+            path: path.clone(),
+            org_id: attribution.org_id,
+            project_id: attribution.project_id,
+            user_id: attribution.user_id,
+            api_key_id: attribution.api_key_id,
+        });
+    }
+
     // 4. Execute the request
     let mut response = next.run(req).await;
 
@@ -876,6 +1111,21 @@ pub async fn api_middleware(
     if let Some(ref warning) = budget_warning {
         response = add_budget_warning_headers(response, warning);
     }
+    let attribution = &state.config.features.attribution_headers;
+    if attribution.enabled {
+        add_attribution_headers(&mut response, attribution, auth_clone.as_ref());
+    }
+
+    if let Some(attribution) = tail_attribution {
+        state.event_bus.publish(request_completed_event(
+            request_id.clone().unwrap_or_default(),
+            method,
+            path,
+            attribution,
+            &response,
+            duration,
+        ));
+    }
 
     // 6. Track usage (async, non-blocking) and adjust budget/token reservations
     if let Some(auth) = auth_clone {
@@ -951,11 +1201,20 @@ pub async fn api_middleware(
                     output_tokens: usage.output_tokens,
                     cost_microcents: usage.cost_microcents,
                 });
+                slo::observe(slo::SloObservation {
+                    provider: &provider,
+                    model: &model,
+                    ttft: None,
+                    latency: Some(elapsed),
+                    failed: response.status().is_server_error(),
+                });
 
                 let header_project_id = headers
                     .get("X-Hadrian-Project")
                     .and_then(|v| v.to_str().ok())
                     .and_then(|v| uuid::Uuid::parse_str(v).ok());
+                let (provider_attempts, suspected_duplicate_attempts) =
+                    ProviderAttempts::usage_fields(response.headers());
 
                 buffer.push(crate::models::UsageLogEntry {
                     request_id: request_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
@@ -992,6 +1251,32 @@ pub async fn api_middleware(
                     tool_results_count: None,
                     tool_runtime_seconds: None,
                     tool_exit_code: None,
+                    jwt_subject: None,
+                    error_code: error_category(&response),
+                    structured_output_repairs: structured_output_repairs(&response),
+                    context_original_tokens: int_header(
+                        &response,
+                        crate::services::context_compression::ORIGINAL_TOKENS_HEADER,
+                    ),
+                    context_compressed_tokens: int_header(
+                        &response,
+                        crate::services::context_compression::COMPRESSED_TOKENS_HEADER,
+                    ),
+                    degraded_from_model: string_header(
+                        &response,
+                        crate::services::model_degradation::DEGRADED_FROM_HEADER,
+                    ),
+                    degradation_reason: string_header(
+                        &response,
+                        crate::services::model_degradation::DEGRADATION_REASON_HEADER,
+                    ),
+                    ttft_ms: None,
+                    inter_token_latency_ms: None,
+                    output_tokens_per_second: None,
+                    idempotency_key: tracker.idempotency_key.clone(),
+                    provider_attempts,
+                    suspected_duplicate_attempts,
+                    tags: tracker.tags.clone(),
                 });
             }
         }
@@ -1000,6 +1285,181 @@ pub async fn api_middleware(
     response
 }
 
+/// Error category to record in usage for a failed response.
+///
+/// Provider errors carry their category in [`ERROR_CATEGORY_HEADER`]; other
+/// errors are classified by status.
+fn error_category(response: &Response) -> Option<String> {
+    let status = response.status();
+    if status.is_success() {
+        return None;
+    }
+    let category = response
+        .headers()
+        .get(ERROR_CATEGORY_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(String::from)
+        .unwrap_or_else(|| ErrorCategory::classify(status, "", "").as_str().to_string());
+    Some(category)
+}
+
+/// Structured output repairs made for the response, when its schema was
+/// validated (see [`crate::services::structured_output`]).
+fn structured_output_repairs(response: &Response) -> Option<i32> {
+    int_header(response, crate::services::structured_output::REPAIRS_HEADER)
+}
+
+/// An integer response header set by a handler for usage tracking.
+fn int_header(response: &Response, name: &str) -> Option<i32> {
+    response
+        .headers()
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+}
+
+/// Response header with the request's cost in US dollars.
+pub const COST_USD_HEADER: &str = "x-hadrian-cost-usd";
+/// Response header with the request's input tokens.
+pub const INPUT_TOKENS_HEADER: &str = "x-hadrian-input-tokens";
+/// Response header with the request's output tokens.
+pub const OUTPUT_TOKENS_HEADER: &str = "x-hadrian-output-tokens";
+/// Response header with the provider that served the request.
+pub const PROVIDER_HEADER: &str = "x-hadrian-provider";
+/// Response header with the request's response cache status.
+pub const CACHE_HEADER: &str = "x-hadrian-cache";
+
+/// Add the `X-Hadrian-*` attribution headers, derived from the cost, token,
+/// provider and cache headers set by handlers.
+///
+/// Headers whose source isn't set are left out: streamed responses have no
+/// cost or tokens yet. Cost and provider are also left out for untrusted
+/// API keys when `redact_untrusted` is set.
+fn add_attribution_headers(
+    response: &mut Response,
+    config: &AttributionHeadersConfig,
+    auth: Option<&AuthenticatedRequest>,
+) {
+    let trusted = !config.redact_untrusted
+        || auth.and_then(|a| a.api_key()).is_none_or(|api_key| {
+            api_key.key.has_scope(
+                &RequiredScope::new(ApiKeyScope::Admin, ScopeAccess::Read).with_area(Some("usage")),
+            )
+        });
+
+    let headers = response.headers();
+    let cost = headers
+        .get("X-Cost-Microcents")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<i64>().ok())
+        .filter(|&microcents| microcents >= 0 && trusted)
+        .map(|microcents| format!("{}.{:06}", microcents / 1_000_000, microcents % 1_000_000));
+    let input_tokens = headers.get("X-Input-Tokens").cloned();
+    let output_tokens = headers.get("X-Output-Tokens").cloned();
+    let provider = headers.get("X-Provider").filter(|_| trusted).cloned();
+    let cache = match headers.get("X-Cache").and_then(|v| v.to_str().ok()) {
+        Some("HIT") => "hit",
+        Some("SEMANTIC_HIT") => "semantic_hit",
+        _ => "miss",
+    };
+
+    let headers = response.headers_mut();
+    if let Some(value) = cost.and_then(|c| c.parse().ok()) {
+        headers.insert(COST_USD_HEADER, value);
+    }
+    if let Some(value) = input_tokens {
+        headers.insert(INPUT_TOKENS_HEADER, value);
+    }
+    if let Some(value) = output_tokens {
+        headers.insert(OUTPUT_TOKENS_HEADER, value);
+    }
+    if let Some(value) = provider {
+        headers.insert(PROVIDER_HEADER, value);
+    }
+    headers.insert(CACHE_HEADER, axum::http::HeaderValue::from_static(cache));
+}
+
+/// A string response header set by a handler for usage tracking.
+fn string_header(response: &Response, name: &str) -> Option<String> {
+    response
+        .headers()
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(String::from)
+}
+
+/// Who a request is attributed to, for admin request tail events.
+struct RequestTailAttribution {
+    org_id: Option<uuid::Uuid>,
+    project_id: Option<uuid::Uuid>,
+    user_id: Option<uuid::Uuid>,
+    api_key_id: Option<uuid::Uuid>,
+}
+
+impl RequestTailAttribution {
+    fn new(state: &AppState, auth: Option<&AuthenticatedRequest>) -> Self {
+        match auth {
+            Some(auth) => {
+                let api_key = auth.api_key();
+                Self {
+                    org_id: api_key
+                        .and_then(|k| k.org_id)
+                        .or_else(|| auth.principal().org_id()),
+                    project_id: auth.project_id(),
+                    user_id: auth.user_id(),
+                    api_key_id: api_key.map(|k| k.key.id),
+                }
+            }
+            // Anonymous requests are attributed to the default user/org
+            None => Self {
+                org_id: state.default_org_id,
+                project_id: None,
+                user_id: state.default_user_id,
+                api_key_id: None,
+            },
+        }
+    }
+}
+
+/// Summarize a finished request for the admin request tail.
+///
+/// Only headers set by handlers for usage tracking are read; streamed
+/// responses report no tokens or cost since those arrive with the stream.
+fn request_completed_event(
+    request_id: String,
+    method: String,
+    path: String,
+    attribution: RequestTailAttribution,
+    response: &Response,
+    latency: Duration,
+) -> ServerEvent {
+    let streamed = response
+        .headers()
+        .get(http::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|s| s.contains("text/event-stream"));
+    let usage = extract_full_usage_from_response(response);
+
+    ServerEvent::RequestCompleted {
+        request_id,
+        timestamp: Utc::now(),
+        method,
+        path,
+        model: string_header(response, "X-Model"),
+        provider: string_header(response, "X-Provider"),
+        org_id: attribution.org_id,
+        project_id: attribution.project_id,
+        user_id: attribution.user_id,
+        api_key_id: attribution.api_key_id,
+        status_code: response.status().as_u16(),
+        latency_ms: latency.as_millis().min(u64::MAX as u128) as u64,
+        streamed,
+        input_tokens: usage.input_tokens,
+        output_tokens: usage.output_tokens,
+        cost_microcents: usage.cost_microcents,
+    }
+}
+
 /// Track usage asynchronously (fire and forget)
 ///
 /// Uses the usage buffer for batched database writes when available,
@@ -1091,6 +1551,16 @@ fn track_usage_async(ctx: UsageTrackingContext<'_>) {
             output_tokens,
             cost_microcents,
         });
+        // Streamed responses are counted towards SLOs when the stream ends
+        if !is_streaming {
+            slo::observe(slo::SloObservation {
+                provider: &provider,
+                model: &model,
+                ttft: None,
+                latency: Some(elapsed),
+                failed: response.status().is_server_error(),
+            });
+        }
     }
 
     // Derive principal-based attribution context
@@ -1102,8 +1572,8 @@ fn track_usage_async(ctx: UsageTrackingContext<'_>) {
     // user_id: from identity (session) or user-owned API key
     let user_id = auth.user_id();
 
-    // project_id: from API key scope, or from X-Hadrian-Project header
-    let project_id = api_key.and_then(|k| k.project_id).or(header_project_id);
+    // project_id: from API key scope or JWT claim, or from X-Hadrian-Project header
+    let project_id = auth.project_id().or(header_project_id);
 
     // team_id: from team-scoped API key only (not a session selection)
     let team_id = api_key.and_then(|k| k.team_id);
@@ -1119,6 +1589,8 @@ fn track_usage_async(ctx: UsageTrackingContext<'_>) {
         .clone()
         .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
 
+    let (provider_attempts, suspected_duplicate_attempts) =
+        ProviderAttempts::usage_fields(response.headers());
     let entry = crate::models::UsageLogEntry {
         request_id: usage_request_id,
         api_key_id,
@@ -1155,6 +1627,32 @@ fn track_usage_async(ctx: UsageTrackingContext<'_>) {
         tool_results_count: None,
         tool_runtime_seconds: None,
         tool_exit_code: None,
+        jwt_subject: auth.bearer_jwt().map(|jwt| jwt.subject.clone()),
+        error_code: error_category(response),
+        structured_output_repairs: structured_output_repairs(response),
+        context_original_tokens: int_header(
+            response,
+            crate::services::context_compression::ORIGINAL_TOKENS_HEADER,
+        ),
+        context_compressed_tokens: int_header(
+            response,
+            crate::services::context_compression::COMPRESSED_TOKENS_HEADER,
+        ),
+        degraded_from_model: string_header(
+            response,
+            crate::services::model_degradation::DEGRADED_FROM_HEADER,
+        ),
+        degradation_reason: string_header(
+            response,
+            crate::services::model_degradation::DEGRADATION_REASON_HEADER,
+        ),
+        ttft_ms: None,
+        inter_token_latency_ms: None,
+        output_tokens_per_second: None,
+        idempotency_key: tracker.idempotency_key,
+        provider_attempts,
+        suspected_duplicate_attempts,
+        tags: tracker.tags,
     };
 
     let is_success = response.status().is_success();
@@ -1265,6 +1763,21 @@ async fn try_authenticate(
     #[cfg(not(feature = "sso"))]
     let _ = (cookies, &api_key_config);
 
+    // Exchanged tokens act as the API key they were minted from, narrowed to
+    // the token's scopes, so they're checked before any other Bearer handling.
+    #[cfg(feature = "jwt")]
+    if let Some(api_key) = try_exchange_token_auth(headers, state).await? {
+        return Ok(AuthenticatedRequest::new(IdentityKind::ApiKey(Box::new(
+            api_key,
+        ))));
+    }
+    // Externally-issued JWTs from `[[auth.jwt]]` issuers are accepted in every
+    // mode, ahead of the mode's own credentials.
+    #[cfg(feature = "jwt")]
+    if let Some(auth) = try_bearer_jwt_auth(headers, state).await? {
+        return Ok(auth);
+    }
+
     match &state.config.auth.mode {
         AuthMode::None => {
             // Optional auth: try API key if header present, don't require it
@@ -1333,6 +1846,410 @@ async fn try_authenticate(
     }
 }
 
+/// Authenticate a request by its verified client certificate.
+///
+/// Returns `Ok(None)` when the certificate isn't mapped to a service account
+/// (or maps ambiguously), so the caller can fall back to other credentials.
+async fn try_client_cert_auth(
+    cert: &ClientCertificate,
+    state: &AppState,
+) -> Result<Option<AuthenticatedRequest>, AuthError> {
+    let services = state
+        .services
+        .as_ref()
+        .ok_or_else(|| AuthError::Internal("Database not configured".to_string()))?;
+
+    let Some(mapping) = services
+        .client_cert_mappings
+        .resolve(&cert.identifiers())
+        .await
+        .map_err(|e| AuthError::Internal(e.to_string()))?
+    else {
+        tracing::debug!(
+            fingerprint = %cert.fingerprint_sha256,
+            "Client certificate not mapped to a service account"
+        );
+        return Ok(None);
+    };
+
+    let service_account = services
+        .service_accounts
+        .get_by_id(mapping.service_account_id)
+        .await
+        .map_err(|e| AuthError::Internal(e.to_string()))?
+        .ok_or(AuthError::InvalidCredentials)?;
+
+    Ok(Some(AuthenticatedRequest::new(IdentityKind::ClientCert(
+        Box::new(ClientCertAuth {
+            mapping_id: mapping.id,
+            service_account_id: service_account.id,
+            org_id: service_account.org_id,
+            roles: service_account.roles,
+            fingerprint_sha256: cert.fingerprint_sha256.clone(),
+        }),
+    ))))
+}
+
+/// Authenticate a request by a short-lived token minted from an API key
+/// (`POST /api/v1/auth/token`).
+///
+/// Returns `Ok(None)` unless token exchange is enabled and the Bearer token
+/// names its issuer. The parent key is re-checked on every request, so
+/// revoking or expiring it invalidates its tokens immediately.
+#[cfg(feature = "jwt")]
+async fn try_exchange_token_auth(
+    headers: &axum::http::HeaderMap,
+    state: &AppState,
+) -> Result<Option<ApiKeyAuth>, AuthError> {
+    use crate::auth::{ExchangeTokenSigner, exchange_token};
+
+    let Some(signer) = ExchangeTokenSigner::from_config(&state.config.auth.token_exchange) else {
+        return Ok(None);
+    };
+    let Some(auth_value) = headers
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+    else {
+        return Ok(None);
+    };
+    let token = if auth_value.len() >= 7 && auth_value[..7].eq_ignore_ascii_case("bearer ") {
+        &auth_value[7..]
+    } else {
+        return Ok(None);
+    };
+
+    let api_key_config = state.config.auth.api_key_config();
+    if token.starts_with(api_key_config.key_prefix.as_str())
+        || decode_jwt_issuer(token).as_deref() != Some(signer.issuer())
+    {
+        return Ok(None);
+    }
+    if headers.contains_key(api_key_config.header_name.as_str()) {
+        return Err(AuthError::AmbiguousCredentials);
+    }
+
+    let claims = signer.verify(token)?;
+    let key = load_exchange_parent_key(claims.sub, state).await?;
+    if key.revoked_at.is_some() {
+        return Err(AuthError::InvalidApiKey);
+    }
+    if key.expires_at.is_some_and(|exp| exp < Utc::now()) {
+        return Err(AuthError::ExpiredApiKey);
+    }
+
+    tracing::debug!(
+        api_key_id = %key.id,
+        jti = %claims.jti,
+        project_id = ?claims.project_id,
+        "API request authenticated via exchanged token"
+    );
+
+    Ok(Some(exchange_token::apply(key, claims)))
+}
+
+/// Load the API key an exchanged token was minted from.
+///
+/// Cached under [`CacheKeys::api_key_by_id`](crate::cache::CacheKeys::api_key_by_id),
+/// which is invalidated on revoke alongside the hash-keyed entry.
+#[cfg(feature = "jwt")]
+async fn load_exchange_parent_key(
+    id: uuid::Uuid,
+    state: &AppState,
+) -> Result<crate::models::ApiKey, AuthError> {
+    use crate::models::ApiKey;
+
+    let cache_key = CacheKeys::api_key_by_id(id);
+    if let Some(cache) = &state.cache
+        && let Ok(Some(bytes)) = cache.get_bytes(&cache_key).await
+        && let Ok(key) = serde_json::from_slice::<ApiKey>(&bytes)
+    {
+        metrics::record_cache_operation("api_key", "get", "hit");
+        return Ok(key);
+    }
+
+    let db = state
+        .db
+        .as_ref()
+        .ok_or_else(|| AuthError::Internal("Database not configured".to_string()))?;
+    let key = db
+        .api_keys()
+        .get_by_id(id)
+        .await
+        .map_err(|e| AuthError::Internal(e.to_string()))?
+        .ok_or(AuthError::InvalidApiKey)?;
+
+    if let Some(cache) = &state.cache
+        && let Ok(bytes) = serde_json::to_vec(&key)
+    {
+        let ttl = std::time::Duration::from_secs(state.config.cache.ttl().api_key_secs);
+        let _ = cache.set_bytes(&cache_key, &bytes, ttl).await;
+    }
+    Ok(key)
+}
+
+/// Authenticate a request by a JWT from an issuer listed in `[[auth.jwt]]`.
+///
+/// Returns `Ok(None)` when there is no Bearer token, the token looks like an
+/// API key, or its issuer isn't configured, so the caller falls through to
+/// the mode's own credentials (including per-org SSO JWTs in `idp` mode).
+/// Once a configured issuer matches, validation failures are fatal.
+#[cfg(feature = "jwt")]
+async fn try_bearer_jwt_auth(
+    headers: &axum::http::HeaderMap,
+    state: &AppState,
+) -> Result<Option<AuthenticatedRequest>, AuthError> {
+    let Some(authenticator) = &state.bearer_jwt else {
+        return Ok(None);
+    };
+    let Some(auth_value) = headers
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+    else {
+        return Ok(None);
+    };
+    let token = if auth_value.len() >= 7 && auth_value[..7].eq_ignore_ascii_case("bearer ") {
+        &auth_value[7..]
+    } else {
+        return Ok(None);
+    };
+
+    let api_key_config = state.config.auth.api_key_config();
+    if token.starts_with(api_key_config.key_prefix.as_str()) {
+        return Ok(None);
+    }
+    let Some(issuer) = decode_jwt_issuer(token).filter(|iss| authenticator.handles_issuer(iss))
+    else {
+        return Ok(None);
+    };
+    if headers.contains_key(api_key_config.header_name.as_str()) {
+        return Err(AuthError::AmbiguousCredentials);
+    }
+
+    let Some(claims) = authenticator.validate(&issuer, token).await? else {
+        return Ok(None);
+    };
+    let jwt = resolve_bearer_jwt(claims, state).await?;
+
+    tracing::debug!(
+        issuer = %jwt.issuer,
+        sub = %jwt.subject,
+        user_id = ?jwt.user_id,
+        org_id = ?jwt.org_id,
+        project_id = ?jwt.project_id,
+        "API request authenticated via external JWT"
+    );
+
+    Ok(Some(AuthenticatedRequest::new(IdentityKind::BearerJwt(
+        Box::new(jwt),
+    ))))
+}
+
+/// Resolve the org, project, and user named by bearer JWT claims.
+///
+/// Values may be slugs or IDs. Claims that don't resolve are left unset, except
+/// that an issuer with `require_org` rejects tokens without a known org.
+#[cfg(feature = "jwt")]
+async fn resolve_bearer_jwt(
+    claims: crate::auth::BearerJwtClaims,
+    state: &AppState,
+) -> Result<crate::auth::BearerJwtAuth, AuthError> {
+    let internal = |e: crate::db::DbError| AuthError::Internal(e.to_string());
+    let services = state.services.as_ref();
+
+    let org_id = match (&claims.org, services) {
+        (Some(org), Some(services)) => match uuid::Uuid::parse_str(org) {
+            Ok(id) => services.organizations.get_by_id(id).await,
+            Err(_) => services.organizations.get_by_slug(org).await,
+        }
+        .map_err(internal)?
+        .map(|org| org.id),
+        _ => None,
+    };
+    if org_id.is_none() && claims.require_org {
+        tracing::debug!(
+            issuer = %claims.issuer,
+            org = ?claims.org,
+            "External JWT organization claim missing or unknown"
+        );
+        return Err(AuthError::Forbidden(
+            "Token does not name a known organization".to_string(),
+        ));
+    }
+
+    let project_id = match (&claims.project, org_id, services) {
+        (Some(project), Some(org_id), Some(services)) => match uuid::Uuid::parse_str(project) {
+            Ok(id) => services.projects.get_by_id_and_org(id, org_id).await,
+            Err(_) => services.projects.get_by_slug(org_id, project).await,
+        }
+        .map_err(internal)?
+        .map(|project| project.id),
+        _ => None,
+    };
+
+    // Users are matched within the issuer's namespace, so one trusted issuer
+    // can't name users provisioned for another IdP.
+    let user_id = match (&claims.user, services) {
+        (Some(user), Some(services)) => services
+            .users
+            .get_by_external_id(&crate::auth::bearer_jwt_external_id(&claims.issuer, user))
+            .await
+            .map_err(internal)?
+            .map(|user| user.id),
+        _ => None,
+    };
+
+    // A token naming both a user and an org or project must not attribute
+    // requests to a user outside them.
+    if let (Some(user_id), Some(services)) = (user_id, services) {
+        if let Some(org_id) = org_id
+            && !services
+                .users
+                .get_org_memberships_for_user(user_id)
+                .await
+                .map_err(internal)?
+                .iter()
+                .any(|m| m.org_id == org_id)
+        {
+            tracing::debug!(
+                issuer = %claims.issuer,
+                %user_id,
+                %org_id,
+                "External JWT user is not a member of its organization"
+            );
+            return Err(AuthError::Forbidden(
+                "Token user is not a member of its organization".to_string(),
+            ));
+        }
+        if let Some(project_id) = project_id
+            && !services
+                .users
+                .get_project_memberships_for_user(user_id)
+                .await
+                .map_err(internal)?
+                .iter()
+                .any(|m| m.project_id == project_id)
+        {
+            tracing::debug!(
+                issuer = %claims.issuer,
+                %user_id,
+                %project_id,
+                "External JWT user is not a member of its project"
+            );
+            return Err(AuthError::Forbidden(
+                "Token user is not a member of its project".to_string(),
+            ));
+        }
+    }
+
+    Ok(crate::auth::BearerJwtAuth {
+        issuer: claims.issuer,
+        subject: claims.subject,
+        user_id,
+        org_id,
+        project_id,
+        roles: claims.roles,
+        email: claims.email,
+        name: claims.name,
+    })
+}
+
+/// How long an organization's network policy is cached.
+const ORG_NETWORK_POLICY_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Load an organization's network policy, consulting the cache first.
+///
+/// Lookup failures fail closed: a key must not be usable from anywhere just
+/// because the policy restricting it cannot be read.
+async fn org_network_policy(
+    state: &AppState,
+    org_id: uuid::Uuid,
+) -> Result<Option<crate::models::OrgNetworkPolicy>, AuthError> {
+    let Some(db) = &state.db else {
+        return Ok(None);
+    };
+
+    let cache_key = CacheKeys::org_network_policy(org_id);
+    if let Some(cache) = &state.cache
+        && let Ok(Some(bytes)) = cache.get_bytes(&cache_key).await
+        && let Ok(policy) = serde_json::from_slice(&bytes)
+    {
+        return Ok(policy);
+    }
+
+    let policy = db
+        .organizations()
+        .get_by_id(org_id)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, %org_id, "Failed to load organization network policy");
+            AuthError::Internal("Unable to load organization network policy".to_string())
+        })?
+        .and_then(|org| org.network_policy);
+
+    if let Some(cache) = &state.cache
+        && let Ok(bytes) = serde_json::to_vec(&policy)
+    {
+        let _ = cache
+            .set_bytes(&cache_key, &bytes, ORG_NETWORK_POLICY_CACHE_TTL)
+            .await;
+    }
+
+    Ok(policy)
+}
+
+/// Requests-per-minute cap that anomaly detection placed on an API key, if any.
+async fn anomaly_rate_limit(state: &AppState, api_key_id: uuid::Uuid) -> Option<u32> {
+    if state
+        .config
+        .features
+        .anomaly_detection
+        .auto_rate_limit
+        .is_none()
+    {
+        return None;
+    }
+    let cache = state.cache.as_ref()?;
+    let bytes = cache
+        .get_bytes(&CacheKeys::anomaly_rate_limit(api_key_id))
+        .await
+        .ok()??;
+    serde_json::from_slice(&bytes).ok()
+}
+
+/// Record a request rejected by an organization network policy in the audit log.
+fn log_network_policy_denial(
+    state: &AppState,
+    api_key: &ApiKeyAuth,
+    org_id: uuid::Uuid,
+    ip: &str,
+    path: &str,
+) {
+    let Some(db) = state.db.clone() else { return };
+    let audit = CreateAuditLog {
+        actor_type: AuditActorType::ApiKey,
+        actor_id: Some(api_key.key.id),
+        action: "network_policy.denied".to_string(),
+        resource_type: "organization".to_string(),
+        resource_id: org_id,
+        org_id: Some(org_id),
+        project_id: api_key.project_id,
+        details: serde_json::json!({
+            "api_key_prefix": api_key.key.key_prefix,
+            "client_ip": ip,
+            "path": path,
+        }),
+        ip_address: Some(ip.to_string()),
+        user_agent: None,
+    };
+
+    #[cfg(feature = "server")]
+    state.task_tracker.spawn(async move {
+        if let Err(e) = db.audit_logs().create(audit).await {
+            tracing::warn!(error = %e, "Failed to log network policy denial");
+        }
+    });
+}
+
 /// Try to authenticate via API key.
 ///
 /// Checks for API keys in the following order:
@@ -1387,7 +2304,8 @@ pub(crate) async fn try_api_key_auth(
         return Err(AuthError::InvalidApiKeyFormat);
     }
 
-    let key_hash = hash_api_key(&raw_key);
+    let hasher = ApiKeyHasher::from_config(api_key_config);
+    let key_hash = hasher.lookup_hash(&raw_key);
 
     // Try cache first if available
     // Cache is invalidated on revoke, so we can trust cached data for the TTL
@@ -1410,6 +2328,7 @@ pub(crate) async fn try_api_key_auth(
                         user_id: cached.user_id,
                         service_account_id: cached.service_account_id,
                         service_account_roles: cached.service_account_roles,
+                        exchanged: None,
                     };
 
                     // Check revocation and expiration from cached data
@@ -1454,9 +2373,10 @@ pub(crate) async fn try_api_key_auth(
         .as_ref()
         .ok_or_else(|| AuthError::Internal("Database not configured".to_string()))?;
 
-    let key_with_owner = db
-        .api_keys()
-        .get_by_hash(&key_hash)
+    let FoundApiKey {
+        key: key_with_owner,
+        key_hash,
+    } = find_api_key(db, &hasher, &raw_key)
         .await
         .map_err(|e| AuthError::Internal(e.to_string()))?
         .ok_or(AuthError::InvalidApiKey)?;
@@ -1469,6 +2389,7 @@ pub(crate) async fn try_api_key_auth(
         user_id: key_with_owner.user_id,
         service_account_id: key_with_owner.service_account_id,
         service_account_roles: key_with_owner.service_account_roles,
+        exchanged: None,
     };
 
     if api_key_auth.is_revoked() {
@@ -1878,7 +2799,7 @@ async fn try_jwt_api_auth(
 
 /// Decode the `iss` claim from a JWT without verifying the signature.
 /// This is a cheap base64 decode of the payload used for routing to the right validator.
-#[cfg(any(feature = "sso", test))]
+#[cfg(any(feature = "sso", feature = "jwt", test))]
 fn decode_jwt_issuer(token: &str) -> Option<String> {
     use base64::Engine;
 
@@ -2093,12 +3014,16 @@ fn period_to_budget_type(period: BudgetPeriod) -> BudgetType {
 /// Log a budget warning event to the audit log (fire-and-forget, once per period)
 ///
 /// Uses cache to deduplicate: only logs once per API key per budget period.
-/// This prevents flooding the audit log with repeated warnings.
+/// This prevents flooding the audit log with repeated warnings. When
+/// `[notifications.spend_alerts]` is enabled, the alert email is sent along
+/// with the audit event.
 /// Note: WebSocket events are always published for real-time monitoring.
 fn log_budget_warning(event: BudgetWarningEvent<'_>) {
     let BudgetWarningEvent {
         state,
         api_key_id,
+        api_key_name,
+        api_key_prefix,
         org_id,
         project_id,
         spend_percentage,
@@ -2133,6 +3058,17 @@ fn log_budget_warning(event: BudgetWarningEvent<'_>) {
     let cache = cache.clone();
     let path = request_path.to_string();
     let req_id = request_id.map(String::from);
+    let spend_alert = state
+        .email_notifier
+        .clone()
+        .filter(|_| state.config.notifications.spend_alerts.enabled)
+        .map(|notifier| {
+            (
+                notifier,
+                state.config.notifications.spend_alerts.clone(),
+                format!("API key \"{}\" ({})", api_key_name, api_key_prefix),
+            )
+        });
 
     // Fire-and-forget: spawn a task to log the audit event
     #[cfg(feature = "server")]
@@ -2185,6 +3121,30 @@ fn log_budget_warning(event: BudgetWarningEvent<'_>) {
                         "Failed to log budget.warning audit event"
                     );
                 }
+
+                if let Some((notifier, alerts_config, scope)) = spend_alert
+                    && let Err(e) = send_spend_alert(
+                        &notifier,
+                        &db,
+                        &alerts_config,
+                        SpendAlert {
+                            org_id,
+                            project_id,
+                            scope,
+                            spend_percentage,
+                            current_spend_cents,
+                            limit_cents,
+                            period: period.as_str(),
+                        },
+                    )
+                    .await
+                {
+                    tracing::warn!(
+                        error = %e,
+                        api_key_id = %api_key_id,
+                        "Failed to send spend alert email"
+                    );
+                }
             }
             Ok(false) => {
                 // Flag already exists - we've already logged this period
@@ -2275,6 +3235,8 @@ mod tests {
             key_prefix: key_prefix.to_string(),
             generation_prefix: None,
             hash_algorithm: HashAlgorithm::default(),
+            pepper: None,
+            legacy_fallback: true,
             cache_ttl_secs: 300,
         });
 
@@ -2287,8 +3249,18 @@ mod tests {
             dlq: None,
             pricing: Arc::new(crate::pricing::PricingConfig::default()),
             circuit_breakers: crate::providers::CircuitBreakerRegistry::new(),
+            fair_queues: crate::providers::FairQueueRegistry::new(),
+            adaptive_limiters: crate::providers::AdaptiveConcurrencyRegistry::new(),
+            provider_settings: crate::providers::ProviderSettingsRegistry::new(
+                crate::providers::CircuitBreakerRegistry::new(),
+                crate::providers::FairQueueRegistry::new(),
+            ),
+            request_smoother: None,
+            token_buckets: None,
             provider_health: crate::jobs::ProviderHealthStateRegistry::new(),
+            job_scheduler: Arc::new(crate::jobs::JobScheduler::new(None)),
             task_tracker: TaskTracker::new(),
+            drain: Arc::new(crate::middleware::DrainState::new()),
             usage_drain: {
                 let tracker = TaskTracker::new();
                 crate::streaming::UsageDrainHandle::spawn(&tracker, 16)
@@ -2298,12 +3270,21 @@ mod tests {
             #[cfg(feature = "saml")]
             saml_registry: None,
             gateway_jwt_registry: None,
+            bearer_jwt: None,
             policy_registry: None,
+            request_policies: None,
             usage_buffer: None,
             response_cache: None,
+            embeddings_cache: None,
             semantic_cache: None,
             input_guardrails: None,
             output_guardrails: None,
+            transforms: None,
+            shadow_traffic: None,
+            idempotency: None,
+            token_counter: None,
+            embedding_batcher: None,
+            email_notifier: None,
             event_bus: Arc::new(crate::events::EventBus::new()),
             file_search_service: None,
             shell_runtime: None,
@@ -2344,6 +3325,8 @@ mod tests {
             key_prefix: key_prefix.to_string(),
             generation_prefix: None,
             hash_algorithm: HashAlgorithm::default(),
+            pepper: None,
+            legacy_fallback: true,
             cache_ttl_secs: 300,
         });
 
@@ -2356,8 +3339,18 @@ mod tests {
             dlq: None,
             pricing: Arc::new(crate::pricing::PricingConfig::default()),
             circuit_breakers: crate::providers::CircuitBreakerRegistry::new(),
+            fair_queues: crate::providers::FairQueueRegistry::new(),
+            adaptive_limiters: crate::providers::AdaptiveConcurrencyRegistry::new(),
+            provider_settings: crate::providers::ProviderSettingsRegistry::new(
+                crate::providers::CircuitBreakerRegistry::new(),
+                crate::providers::FairQueueRegistry::new(),
+            ),
+            request_smoother: None,
+            token_buckets: None,
             provider_health: crate::jobs::ProviderHealthStateRegistry::new(),
+            job_scheduler: Arc::new(crate::jobs::JobScheduler::new(None)),
             task_tracker: TaskTracker::new(),
+            drain: Arc::new(crate::middleware::DrainState::new()),
             usage_drain: {
                 let tracker = TaskTracker::new();
                 crate::streaming::UsageDrainHandle::spawn(&tracker, 16)
@@ -2367,12 +3360,21 @@ mod tests {
             #[cfg(feature = "saml")]
             saml_registry: None,
             gateway_jwt_registry: None,
+            bearer_jwt: None,
             policy_registry: None,
+            request_policies: None,
             usage_buffer: None,
             response_cache: None,
+            embeddings_cache: None,
             semantic_cache: None,
             input_guardrails: None,
             output_guardrails: None,
+            transforms: None,
+            shadow_traffic: None,
+            idempotency: None,
+            token_counter: None,
+            embedding_batcher: None,
+            email_notifier: None,
             event_bus: Arc::new(crate::events::EventBus::new()),
             file_search_service: None,
             shell_runtime: None,
@@ -2658,6 +3660,151 @@ mod tests {
         assert!(matches!(result, Err(AuthError::MissingCredentials)));
     }
 
+    #[cfg(feature = "jwt")]
+    #[tokio::test]
+    async fn test_try_bearer_jwt_auth_routing() {
+        use base64::Engine;
+
+        let mut state = create_multi_auth_state("X-API-Key", "gw_");
+        let jwt_config: crate::config::BearerJwtConfig = toml::from_str(
+            r#"
+            issuer = "https://auth.example.com/"
+            audience = "hadrian"
+            jwks_url = "https://auth.example.com/jwks.json"
+            "#,
+        )
+        .unwrap();
+        state.bearer_jwt = Some(Arc::new(
+            crate::auth::BearerJwtAuthenticator::new(&[jwt_config], Default::default()).unwrap(),
+        ));
+
+        let token = |iss: &str| {
+            let encode = |s: &str| base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(s);
+            format!(
+                "Bearer {}.{}.sig",
+                encode(r#"{"alg":"RS256","kid":"k1"}"#),
+                encode(&format!(r#"{{"iss":"{iss}","sub":"job"}}"#))
+            )
+        };
+
+        // Unconfigured issuers and API keys fall through to the mode's credentials
+        let other = token("https://idp.other.com");
+        let headers = make_headers(vec![("Authorization", other.as_str())]);
+        assert!(
+            try_bearer_jwt_auth(&headers, &state)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        let headers = make_headers(vec![("Authorization", "Bearer gw_test_key")]);
+        assert!(
+            try_bearer_jwt_auth(&headers, &state)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        // A configured issuer alongside an API key header is ambiguous
+        let configured = token("https://auth.example.com/");
+        let headers = make_headers(vec![
+            ("Authorization", configured.as_str()),
+            ("X-API-Key", "gw_test_key"),
+        ]);
+        assert!(matches!(
+            try_bearer_jwt_auth(&headers, &state).await,
+            Err(AuthError::AmbiguousCredentials)
+        ));
+    }
+
+    #[cfg(feature = "jwt")]
+    #[tokio::test]
+    async fn test_resolve_bearer_jwt_scopes_users() {
+        use crate::{
+            auth::BearerJwtClaims,
+            models::{CreateOrganization, CreateUser, MembershipSource},
+        };
+
+        let config = crate::config::GatewayConfig::parse(
+            r#"
+[database]
+type = "sqlite"
+path = "file:api_layer_bearer_jwt_users?mode=memory&cache=shared"
+create_if_missing = true
+run_migrations = true
+wal_mode = false
+
+[providers]
+default_provider = "test"
+
+[providers.test]
+type = "test"
+model_name = "test-model"
+"#,
+        )
+        .unwrap();
+        let state = AppState::new(config).await.unwrap();
+        let services = state.services.as_ref().unwrap();
+
+        let org = |slug: &str| CreateOrganization {
+            slug: slug.to_string(),
+            name: slug.to_string(),
+        };
+        let acme = services.organizations.create(org("acme")).await.unwrap();
+        services.organizations.create(org("globex")).await.unwrap();
+        let user = |external_id: &str| CreateUser {
+            external_id: external_id.to_string(),
+            email: None,
+            name: None,
+        };
+        let alice = services
+            .users
+            .create(user("https://a.example.com/|alice"))
+            .await
+            .unwrap();
+        services
+            .users
+            .add_to_org(alice.id, acme.id, "member", MembershipSource::Manual)
+            .await
+            .unwrap();
+        // Same subject, provisioned for another IdP
+        let bob = services.users.create(user("bob")).await.unwrap();
+        services
+            .users
+            .add_to_org(bob.id, acme.id, "member", MembershipSource::Manual)
+            .await
+            .unwrap();
+
+        let claims = |user: &str, org: &str| BearerJwtClaims {
+            issuer: "https://a.example.com/".to_string(),
+            subject: user.to_string(),
+            user: Some(user.to_string()),
+            org: Some(org.to_string()),
+            project: None,
+            roles: vec!["admin".to_string()],
+            email: None,
+            name: None,
+            require_org: true,
+        };
+
+        let jwt = resolve_bearer_jwt(claims("alice", "acme"), &state)
+            .await
+            .unwrap();
+        assert_eq!(jwt.user_id, Some(alice.id));
+        assert_eq!(jwt.org_id, Some(acme.id));
+
+        // Cross-issuer: `bob` belongs to another IdP and isn't matched
+        let jwt = resolve_bearer_jwt(claims("bob", "acme"), &state)
+            .await
+            .unwrap();
+        assert_eq!(jwt.user_id, None);
+
+        // Cross-org: alice isn't a member of globex
+        assert!(matches!(
+            resolve_bearer_jwt(claims("alice", "globex"), &state).await,
+            Err(AuthError::Forbidden(_))
+        ));
+    }
+
     #[test]
     fn test_decode_jwt_issuer_valid() {
         // Build a JWT-like payload with iss claim: {"iss":"https://idp.acme.com","sub":"user1"}
@@ -2705,4 +3852,95 @@ mod tests {
             Some("https://login.microsoftonline.com/tenant".to_string())
         );
     }
+
+    // ========== Attribution header tests ==========
+
+    fn response_with_headers(headers: Vec<(&str, &str)>) -> Response {
+        let mut response = Response::new(Body::empty());
+        *response.headers_mut() = make_headers(headers);
+        response
+    }
+
+    #[test]
+    fn test_attribution_headers_from_usage_headers() {
+        let mut response = response_with_headers(vec![
+            ("X-Cost-Microcents", "1234567"),
+            ("X-Input-Tokens", "120"),
+            ("X-Output-Tokens", "45"),
+            ("X-Provider", "openai"),
+            ("X-Cache", "SEMANTIC_HIT"),
+        ]);
+        add_attribution_headers(&mut response, &AttributionHeadersConfig::default(), None);
+
+        let headers = response.headers();
+        assert_eq!(headers[COST_USD_HEADER], "1.234567");
+        assert_eq!(headers[INPUT_TOKENS_HEADER], "120");
+        assert_eq!(headers[OUTPUT_TOKENS_HEADER], "45");
+        assert_eq!(headers[PROVIDER_HEADER], "openai");
+        assert_eq!(headers[CACHE_HEADER], "semantic_hit");
+    }
+
+    #[test]
+    fn test_attribution_headers_streaming_response() {
+        // Streamed responses have a provider but no cost or tokens yet
+        let mut response = response_with_headers(vec![("X-Provider", "anthropic")]);
+        add_attribution_headers(&mut response, &AttributionHeadersConfig::default(), None);
+
+        let headers = response.headers();
+        assert!(!headers.contains_key(COST_USD_HEADER));
+        assert!(!headers.contains_key(INPUT_TOKENS_HEADER));
+        assert!(!headers.contains_key(OUTPUT_TOKENS_HEADER));
+        assert_eq!(headers[PROVIDER_HEADER], "anthropic");
+        assert_eq!(headers[CACHE_HEADER], "miss");
+    }
+
+    #[test]
+    fn test_request_completed_event() {
+        let org_id = uuid::Uuid::new_v4();
+        let response = response_with_headers(vec![
+            ("X-Model", "gpt-4o"),
+            ("X-Provider", "openai"),
+            ("X-Input-Tokens", "120"),
+            ("X-Output-Tokens", "45"),
+            ("X-Cost-Microcents", "900"),
+        ]);
+        let attribution = RequestTailAttribution {
+            org_id: Some(org_id),
+            project_id: None,
+            user_id: None,
+            api_key_id: None,
+        };
+
+        let event = request_completed_event(
+            "req-1".to_string(),
+            "POST".to_string(),
+            "/v1/chat/completions".to_string(),
+            attribution,
+            &response,
+            Duration::from_millis(250),
+        );
+
+        let ServerEvent::RequestCompleted {
+            model,
+            provider,
+            org_id: event_org_id,
+            status_code,
+            latency_ms,
+            streamed,
+            output_tokens,
+            cost_microcents,
+            ..
+        } = event
+        else {
+            panic!("expected a request_completed event");
+        };
+        assert_eq!(model.as_deref(), Some("gpt-4o"));
+        assert_eq!(provider.as_deref(), Some("openai"));
+        assert_eq!(event_org_id, Some(org_id));
+        assert_eq!(status_code, 200);
+        assert_eq!(latency_ms, 250);
+        assert!(!streamed);
+        assert_eq!(output_tokens, Some(45));
+        assert_eq!(cost_microcents, Some(900));
+    }
 }
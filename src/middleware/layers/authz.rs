@@ -392,6 +392,7 @@ mod tests {
             rotated_from_key_id: None,
             rotation_grace_until: None,
             sovereignty_requirements: None,
+            labels: Default::default(),
         }
     }
 
@@ -411,6 +412,7 @@ mod tests {
             user_id: None,
             service_account_id: Some(sa_id),
             service_account_roles: Some(vec!["deployer".to_string(), "viewer".to_string()]),
+            exchanged: None,
         };
 
         let auth = AuthenticatedRequest::new(IdentityKind::ApiKey(Box::new(api_key_auth)));
@@ -446,6 +448,7 @@ mod tests {
             user_id: None,
             service_account_id: Some(sa_id),
             service_account_roles: Some(vec!["deployer".to_string()]),
+            exchanged: None,
         };
 
         let auth = AuthenticatedRequest::new(IdentityKind::ApiKey(Box::new(api_key_auth)));
@@ -468,6 +471,7 @@ mod tests {
             user_id: None,
             service_account_id: Some(sa_id),
             service_account_roles: Some(vec!["sa_role".to_string()]),
+            exchanged: None,
         };
 
         let identity = Identity {
@@ -509,6 +513,7 @@ mod tests {
             user_id: None,
             service_account_id: None,
             service_account_roles: None,
+            exchanged: None,
         };
 
         let auth = AuthenticatedRequest::new(IdentityKind::ApiKey(Box::new(api_key_auth)));
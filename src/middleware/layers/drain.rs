@@ -0,0 +1,194 @@
+//! Draining for zero-downtime restarts.
+//!
+//! Once an instance starts draining — through `POST /admin/v1/system/drain`
+//! or a shutdown signal — the readiness probe fails so load balancers stop
+//! routing to it, and new data-plane requests are turned away with a 503 and
+//! `Retry-After`. Requests already in flight, including streams, carry on
+//! until they finish; shutdown waits for them up to
+//! `server.shutdown.drain_timeout_secs`.
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use axum::{
+    Json,
+    extract::{Request, State},
+    http::{HeaderValue, StatusCode, header::RETRY_AFTER},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::Notify;
+
+use crate::{
+    AppState, compat::Mutex, observability::metrics, openapi::ErrorResponse,
+    providers::fair_queue::hold_permit,
+};
+
+/// Draining status of this instance.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct DrainStatus {
+    /// Whether the instance is draining
+    pub draining: bool,
+    /// When draining started
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<DateTime<Utc>>,
+    /// Data-plane requests still in flight, including open streams
+    pub in_flight: usize,
+}
+
+/// Tracks whether this instance is draining and how many data-plane
+/// requests it is still serving.
+#[derive(Debug, Default)]
+pub struct DrainState {
+    draining: AtomicBool,
+    started_at: Mutex<Option<DateTime<Utc>>>,
+    in_flight: AtomicUsize,
+    idle: Notify,
+}
+
+impl DrainState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start draining. Returns false if the instance was already draining.
+    pub fn start(&self) -> bool {
+        if self.draining.swap(true, Ordering::SeqCst) {
+            return false;
+        }
+        *self.started_at.lock() = Some(Utc::now());
+        metrics::set_draining(true);
+        tracing::info!(
+            in_flight = self.in_flight(),
+            "Draining: rejecting new requests and failing readiness"
+        );
+        true
+    }
+
+    /// Whether the instance is draining.
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+
+    /// Data-plane requests currently in flight.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    pub fn status(&self) -> DrainStatus {
+        DrainStatus {
+            draining: self.is_draining(),
+            started_at: *self.started_at.lock(),
+            in_flight: self.in_flight(),
+        }
+    }
+
+    /// Count a request as in flight until the returned guard is dropped.
+    pub fn track(self: &Arc<Self>) -> InFlightGuard {
+        let count = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        metrics::set_active_connections(count);
+        InFlightGuard {
+            state: Arc::clone(self),
+        }
+    }
+
+    /// Wait until no requests are in flight, or `timeout` passes. Returns
+    /// whether every request finished.
+    pub async fn wait_idle(&self, timeout: Duration) -> bool {
+        tokio::time::timeout(timeout, async {
+            loop {
+                let idle = self.idle.notified();
+                if self.in_flight() == 0 {
+                    return;
+                }
+                idle.await;
+            }
+        })
+        .await
+        .is_ok()
+    }
+}
+
+/// Marks one data-plane request as in flight; see [`DrainState::track`].
+pub struct InFlightGuard {
+    state: Arc<DrainState>,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        let count = self.state.in_flight.fetch_sub(1, Ordering::SeqCst) - 1;
+        metrics::set_active_connections(count);
+        if count == 0 {
+            self.state.idle.notify_waiters();
+        }
+    }
+}
+
+/// Reject new data-plane requests while draining and count the rest as in
+/// flight until their response body (or stream) is finished.
+pub async fn drain_middleware(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    if state.drain.is_draining() {
+        metrics::record_gateway_error("draining", "server_draining", None);
+        let body = ErrorResponse::with_type(
+            "server_error",
+            "server_draining",
+            "This instance is shutting down; retry the request",
+        );
+        let mut response = (StatusCode::SERVICE_UNAVAILABLE, Json(body)).into_response();
+        let retry_after = state.config.server.shutdown.retry_after_secs;
+        if let Ok(v) = HeaderValue::try_from(retry_after.to_string()) {
+            response.headers_mut().insert(RETRY_AFTER, v);
+        }
+        return response;
+    }
+
+    let guard = state.drain.track();
+    let response = next.run(req).await;
+    hold_permit(response, guard)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_wait_idle_after_guards_drop() {
+        let state = Arc::new(DrainState::new());
+        assert!(state.wait_idle(Duration::from_millis(10)).await);
+
+        let guard = state.track();
+        let other = state.track();
+        assert_eq!(state.in_flight(), 2);
+        assert!(!state.wait_idle(Duration::from_millis(10)).await);
+
+        drop(other);
+        let waiter = {
+            let state = Arc::clone(&state);
+            tokio::spawn(async move { state.wait_idle(Duration::from_secs(5)).await })
+        };
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        drop(guard);
+        assert!(waiter.await.unwrap());
+        assert_eq!(state.in_flight(), 0);
+    }
+
+    #[test]
+    fn test_start_is_idempotent() {
+        let state = DrainState::new();
+        assert!(!state.status().draining);
+        assert!(state.start());
+        let started_at = state.status().started_at;
+        assert!(started_at.is_some());
+        assert!(!state.start());
+        assert_eq!(state.status().started_at, started_at);
+        assert!(state.is_draining());
+    }
+}
@@ -0,0 +1,296 @@
+//! Idempotency-Key middleware.
+//!
+//! Replays the stored response when a client repeats an `Idempotency-Key`
+//! on a completion endpoint, see [`crate::services::idempotency`]. Must run
+//! after [`api_middleware`](super::api::api_middleware): keys are scoped to
+//! the authenticated caller, and replays carry no `X-Model` header so the
+//! budget reserved for them is refunded instead of charged.
+
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{HeaderValue, Method, StatusCode, header::CONTENT_TYPE},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use bytes::Bytes;
+use futures_util::StreamExt;
+
+use crate::{
+    AppState,
+    auth::{AuthenticatedRequest, IdentityKind},
+    models::IdempotentResponse,
+    openapi::ErrorResponse,
+    services::idempotency::{
+        Claim, IDEMPOTENCY_KEY_HEADER, IDEMPOTENT_REPLAYED_HEADER, IdempotencyStore,
+        MAX_KEY_LENGTH, key_hash, request_hash,
+    },
+};
+
+/// Endpoints that honor `Idempotency-Key`.
+const IDEMPOTENT_PATHS: &[&str] = &["/v1/chat/completions", "/v1/completions", "/v1/responses"];
+
+/// Claim the request's `Idempotency-Key`, replaying the stored response for
+/// a repeat and recording the response of a first attempt.
+///
+/// Server errors and `429`s release the key so the client can retry; other
+/// responses, including `4xx`, are stored and replayed. If the store is
+/// unreachable the request runs without idempotency rather than failing.
+pub async fn idempotency_middleware(
+    State(state): State<AppState>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let Some(store) = state.idempotency.clone() else {
+        return next.run(req).await;
+    };
+    if req.method() != Method::POST {
+        return next.run(req).await;
+    }
+    let path = req.uri().path();
+    let path = path.strip_prefix("/api").unwrap_or(path).to_string();
+    if !IDEMPOTENT_PATHS.contains(&path.as_str()) {
+        return next.run(req).await;
+    }
+    let Some(key) = req.headers().get(IDEMPOTENCY_KEY_HEADER) else {
+        return next.run(req).await;
+    };
+    let key = match key.to_str() {
+        Ok(key) if !key.is_empty() && key.len() <= MAX_KEY_LENGTH => key.to_string(),
+        _ => {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                "invalid_idempotency_key",
+                format!("Idempotency-Key must be 1 to {MAX_KEY_LENGTH} visible ASCII characters"),
+            );
+        }
+    };
+
+    let (parts, body) = req.into_parts();
+    let bytes = match axum::body::to_bytes(body, state.config.server.body_limit_bytes).await {
+        Ok(bytes) => bytes,
+        Err(_) => {
+            return error_response(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "payload_too_large",
+                "Request body too large".to_string(),
+            );
+        }
+    };
+
+    let caller = caller_scope(parts.extensions.get::<AuthenticatedRequest>());
+    let key_hash = key_hash(&caller, &path, &key);
+    let request_hash = request_hash(&bytes);
+
+    match store.claim(&key_hash, &request_hash).await {
+        Ok(Claim::Acquired) => {}
+        Ok(Claim::Completed(Some(response))) => return replay(response),
+        Ok(Claim::Completed(None)) => {
+            return error_response(
+                StatusCode::CONFLICT,
+                "idempotency_response_unavailable",
+                "A request with this Idempotency-Key already completed, but its response was too large to store".to_string(),
+            );
+        }
+        Ok(Claim::InProgress) => {
+            return error_response(
+                StatusCode::CONFLICT,
+                "idempotency_key_in_use",
+                "A request with this Idempotency-Key is still in progress".to_string(),
+            );
+        }
+        Ok(Claim::Mismatch) => {
+            return error_response(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "idempotency_key_reused",
+                "This Idempotency-Key was already used with a different request body".to_string(),
+            );
+        }
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to claim idempotency key; running request without it");
+            return next
+                .run(Request::from_parts(parts, Body::from(bytes)))
+                .await;
+        }
+    }
+
+    let response = next
+        .run(Request::from_parts(parts, Body::from(bytes)))
+        .await;
+
+    let status = response.status();
+    if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+        if let Err(e) = store.release(&key_hash).await {
+            tracing::warn!(error = %e, "Failed to release idempotency key");
+        }
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let recorder = Recorder {
+        store,
+        key_hash,
+        request_hash,
+        status: status.as_u16(),
+        content_type: parts
+            .headers
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(String::from),
+        body: Vec::new(),
+        truncated: false,
+        finished: false,
+    };
+    // Record the body as it goes out, so streamed responses are stored too
+    let stream = futures_util::stream::unfold(
+        (body.into_data_stream(), recorder),
+        |(mut stream, mut recorder)| async move {
+            match stream.next().await {
+                Some(Ok(chunk)) => {
+                    recorder.push(&chunk);
+                    Some((Ok::<Bytes, axum::Error>(chunk), (stream, recorder)))
+                }
+                Some(Err(e)) => Some((Err(e), (stream, recorder))),
+                None => {
+                    recorder.finish();
+                    None
+                }
+            }
+        },
+    );
+    Response::from_parts(parts, Body::from_stream(stream))
+}
+
+/// Who an idempotency key belongs to. Keys are only matched within the same
+/// caller, so one client can't replay another's response.
+fn caller_scope(auth: Option<&AuthenticatedRequest>) -> String {
+    let Some(auth) = auth else {
+        return "anonymous".to_string();
+    };
+    match &auth.kind {
+        IdentityKind::ApiKey(key) | IdentityKind::Both { api_key: key, .. } => {
+            format!("key:{}", key.key.id)
+        }
+        IdentityKind::ClientCert(cert) => format!("sa:{}", cert.service_account_id),
+        IdentityKind::BearerJwt(jwt) => format!("jwt:{}:{}", jwt.issuer, jwt.subject),
+        IdentityKind::Identity(identity) => match identity.user_id {
+            Some(user_id) => format!("user:{user_id}"),
+            None => format!("idp:{}", identity.external_id),
+        },
+    }
+}
+
+fn replay(stored: IdempotentResponse) -> Response {
+    let status = StatusCode::from_u16(stored.status).unwrap_or(StatusCode::OK);
+    let mut response = (status, stored.body).into_response();
+    let headers = response.headers_mut();
+    headers.remove(CONTENT_TYPE);
+    if let Some(content_type) = stored
+        .content_type
+        .and_then(|ct| HeaderValue::from_str(&ct).ok())
+    {
+        headers.insert(CONTENT_TYPE, content_type);
+    }
+    headers.insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+    response
+}
+
+fn error_response(status: StatusCode, code: &str, message: String) -> Response {
+    (status, axum::Json(ErrorResponse::new(code, message))).into_response()
+}
+
+/// Accumulates a response body and stores it once the body ends. Dropped
+/// before then (client disconnect, body error), it releases the key instead.
+struct Recorder {
+    store: Arc<IdempotencyStore>,
+    key_hash: String,
+    request_hash: String,
+    status: u16,
+    content_type: Option<String>,
+    body: Vec<u8>,
+    truncated: bool,
+    finished: bool,
+}
+
+impl Recorder {
+    fn push(&mut self, chunk: &[u8]) {
+        if self.truncated {
+            return;
+        }
+        if self.body.len() + chunk.len() > self.store.max_response_bytes() {
+            self.truncated = true;
+            self.body = Vec::new();
+        } else {
+            self.body.extend_from_slice(chunk);
+        }
+    }
+
+    fn finish(&mut self) {
+        self.finished = true;
+        let response = (!self.truncated).then(|| IdempotentResponse {
+            status: self.status,
+            content_type: self.content_type.take(),
+            body: std::mem::take(&mut self.body),
+        });
+        let store = self.store.clone();
+        let key_hash = std::mem::take(&mut self.key_hash);
+        let request_hash = std::mem::take(&mut self.request_hash);
+        spawn(async move {
+            if let Err(e) = store.complete(&key_hash, &request_hash, response).await {
+                tracing::warn!(error = %e, "Failed to store idempotent response");
+            }
+        });
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        if self.finished {
+            return;
+        }
+        let store = self.store.clone();
+        let key_hash = std::mem::take(&mut self.key_hash);
+        spawn(async move {
+            if let Err(e) = store.release(&key_hash).await {
+                tracing::warn!(error = %e, "Failed to release idempotency key");
+            }
+        });
+    }
+}
+
+fn spawn(task: impl Future<Output = ()> + Send + 'static) {
+    if let Ok(handle) = tokio::runtime::Handle::try_current() {
+        handle.spawn(task);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replay_restores_status_and_content_type() {
+        let response = replay(IdempotentResponse {
+            status: 400,
+            content_type: Some("text/event-stream".to_string()),
+            body: b"data: [DONE]\n\n".to_vec(),
+        });
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            response.headers().get(CONTENT_TYPE).unwrap(),
+            "text/event-stream"
+        );
+        assert_eq!(
+            response.headers().get(IDEMPOTENT_REPLAYED_HEADER).unwrap(),
+            "true"
+        );
+        assert!(response.headers().get("X-Model").is_none());
+    }
+
+    #[test]
+    fn test_anonymous_callers_share_a_scope() {
+        assert_eq!(caller_scope(None), "anonymous");
+    }
+}
@@ -1,6 +1,12 @@
 pub mod admin;
 pub mod api;
 pub mod authz;
+pub mod drain;
+pub mod idempotency;
+pub mod parameter_governance;
+pub mod provenance;
 pub mod rate_limit;
 pub mod request_id;
+pub mod request_policies;
 pub mod security_headers;
+pub mod transforms;
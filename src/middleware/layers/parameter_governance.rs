@@ -0,0 +1,285 @@
+//! Organization and project parameter governance.
+//!
+//! Enforces the [`ParameterGovernance`] rules set on the caller's
+//! organization and project (see [`crate::transforms::governance`]). Runs
+//! after [`request_policies_middleware`](super::request_policies::request_policies_middleware)
+//! and before [`transforms_middleware`](super::transforms::transforms_middleware).
+
+use std::time::Duration;
+
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{StatusCode, header::CONTENT_LENGTH},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use uuid::Uuid;
+
+use crate::{
+    AppState, auth::AuthenticatedRequest, cache::CacheKeys, config::TransformEndpoint,
+    models::ParameterGovernance, openapi::ErrorResponse, transforms::governance,
+};
+
+/// How long organization and project governance rules are cached.
+const GOVERNANCE_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Clamp or reject data-plane requests per the caller's parameter
+/// governance rules.
+///
+/// Organization rules run before project rules. The organization and project
+/// are the credential's, or else the session user's (see
+/// [`AuthenticatedRequest::effective_org_id`]). Requests without an
+/// organization pass through, as do bodies that aren't JSON objects so the
+/// handler reports the parse error as usual.
+pub async fn parameter_governance_middleware(
+    State(state): State<AppState>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let Some(endpoint) = TransformEndpoint::from_path(req.uri().path()) else {
+        return next.run(req).await;
+    };
+    let Some((org_id, project_id)) = req
+        .extensions()
+        .get::<AuthenticatedRequest>()
+        .and_then(|auth| Some((auth.effective_org_id()?, auth.effective_project_id())))
+    else {
+        return next.run(req).await;
+    };
+
+    let org_rules = match org_governance(&state, org_id).await {
+        Ok(rules) => rules,
+        Err(response) => return response,
+    };
+    let project_rules = match project_id {
+        Some(project_id) => match project_governance(&state, project_id).await {
+            Ok(rules) => rules,
+            Err(response) => return response,
+        },
+        None => None,
+    };
+    if org_rules.is_none() && project_rules.is_none() {
+        return next.run(req).await;
+    }
+
+    let (mut parts, body) = req.into_parts();
+    let bytes = match axum::body::to_bytes(body, state.config.server.body_limit_bytes).await {
+        Ok(bytes) => bytes,
+        Err(_) => {
+            return error_response(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "payload_too_large",
+                "Request body too large".to_string(),
+            );
+        }
+    };
+    let mut json = match serde_json::from_slice::<serde_json::Value>(&bytes) {
+        Ok(json) if json.is_object() => json,
+        _ => {
+            return next
+                .run(Request::from_parts(parts, Body::from(bytes)))
+                .await;
+        }
+    };
+
+    let rules = org_rules
+        .iter()
+        .chain(project_rules.iter())
+        .flat_map(|g| g.rules.iter());
+    let outcome = match governance::enforce(rules, endpoint, &mut json) {
+        Ok(outcome) => outcome,
+        Err(violations) => {
+            tracing::info!(
+                %org_id,
+                project_id = ?project_id,
+                endpoint = endpoint.as_str(),
+                ?violations,
+                "Request rejected by parameter governance"
+            );
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                "parameter_governance_violation",
+                format!(
+                    "Request violates parameter governance: {}",
+                    violations.join("; ")
+                ),
+            );
+        }
+    };
+
+    if !outcome.modified() {
+        return next
+            .run(Request::from_parts(parts, Body::from(bytes)))
+            .await;
+    }
+
+    tracing::debug!(
+        %org_id,
+        project_id = ?project_id,
+        clamped = ?outcome.clamped,
+        prompt_added = outcome.prompt_added,
+        "Request adjusted by parameter governance"
+    );
+    let body = serde_json::to_vec(&json).unwrap_or_else(|_| bytes.to_vec());
+    parts.headers.remove(CONTENT_LENGTH);
+    next.run(Request::from_parts(parts, Body::from(body))).await
+}
+
+async fn org_governance(
+    state: &AppState,
+    org_id: Uuid,
+) -> Result<Option<ParameterGovernance>, Response> {
+    let Some(db) = &state.db else {
+        return Ok(None);
+    };
+    load_governance(state, CacheKeys::org_parameter_governance(org_id), async {
+        db.organizations()
+            .get_by_id(org_id)
+            .await
+            .map(|org| org.and_then(|o| o.parameter_governance))
+    })
+    .await
+}
+
+async fn project_governance(
+    state: &AppState,
+    project_id: Uuid,
+) -> Result<Option<ParameterGovernance>, Response> {
+    let Some(db) = &state.db else {
+        return Ok(None);
+    };
+    load_governance(
+        state,
+        CacheKeys::project_parameter_governance(project_id),
+        async {
+            db.projects()
+                .get_by_id(project_id)
+                .await
+                .map(|project| project.and_then(|p| p.parameter_governance))
+        },
+    )
+    .await
+}
+
+/// Load governance rules, consulting the cache first.
+///
+/// Lookup failures fail closed: bounds an admin set must not silently lapse
+/// because they can't be read.
+async fn load_governance(
+    state: &AppState,
+    cache_key: String,
+    load: impl std::future::Future<Output = Result<Option<ParameterGovernance>, crate::db::DbError>>,
+) -> Result<Option<ParameterGovernance>, Response> {
+    if let Some(cache) = &state.cache
+        && let Ok(Some(bytes)) = cache.get_bytes(&cache_key).await
+        && let Ok(governance) = serde_json::from_slice(&bytes)
+    {
+        return Ok(governance);
+    }
+
+    let governance = load.await.map_err(|e| {
+        tracing::error!(error = %e, %cache_key, "Failed to load parameter governance");
+        error_response(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "governance_unavailable",
+            "Parameter governance rules could not be loaded".to_string(),
+        )
+    })?;
+
+    if let Some(cache) = &state.cache
+        && let Ok(bytes) = serde_json::to_vec(&governance)
+    {
+        let _ = cache
+            .set_bytes(&cache_key, &bytes, GOVERNANCE_CACHE_TTL)
+            .await;
+    }
+
+    Ok(governance)
+}
+
+fn error_response(status: StatusCode, code: &str, message: String) -> Response {
+    (status, axum::Json(ErrorResponse::new(code, message))).into_response()
+}
+
+#[cfg(all(test, feature = "database-sqlite"))]
+mod tests {
+    use axum::{Extension, Router, routing::post};
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::{
+        auth::{Identity, IdentityKind},
+        models::CreateOrganization,
+    };
+
+    #[tokio::test]
+    async fn test_org_rules_apply_to_session_identity() {
+        let config = crate::config::GatewayConfig::parse(
+            r#"
+[database]
+type = "sqlite"
+path = "file:governance_session_identity_db?mode=memory&cache=shared"
+create_if_missing = true
+run_migrations = true
+wal_mode = false
+busy_timeout_ms = 5000
+
+[providers.test]
+type = "test"
+"#,
+        )
+        .expect("Failed to parse test config");
+        let state = crate::AppState::new(config)
+            .await
+            .expect("Failed to create AppState");
+        let orgs = state.db.as_ref().unwrap().organizations();
+        let org = orgs
+            .create(CreateOrganization {
+                slug: "governed-session".to_string(),
+                name: "Governed Session".to_string(),
+            })
+            .await
+            .unwrap();
+        let governance: ParameterGovernance = serde_json::from_value(json!({"rules": [{
+            "forbidden_params": ["logit_bias"],
+            "on_violation": "reject",
+        }]}))
+        .unwrap();
+        orgs.set_parameter_governance(org.id, Some(&governance))
+            .await
+            .unwrap();
+
+        // A session user carries their org only through the identity
+        let auth = AuthenticatedRequest::new(IdentityKind::Identity(Identity {
+            external_id: "user-1".to_string(),
+            email: None,
+            name: None,
+            user_id: None,
+            roles: vec![],
+            idp_groups: vec![],
+            org_ids: vec![org.id.to_string()],
+            team_ids: vec![],
+            project_ids: vec![],
+        }));
+        let app = Router::new()
+            .route("/v1/chat/completions", post(|| async { "ok" }))
+            .layer(axum::middleware::from_fn_with_state(
+                state,
+                parameter_governance_middleware,
+            ))
+            .layer(Extension(auth));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/v1/chat/completions")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                r#"{"model": "test/test-model", "logit_bias": {"50256": -100}}"#,
+            ))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,103 @@
+//! Provenance middleware.
+//!
+//! Signs the generated text of completion responses when
+//! `[features.provenance]` is enabled (see
+//! [`crate::services::provenance`]). Runs outside
+//! [`transforms_middleware`](super::transforms::transforms_middleware) so the
+//! signature covers the text the client actually receives, and inside
+//! [`idempotency_middleware`](super::idempotency::idempotency_middleware) so
+//! replayed responses keep the original metadata.
+
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{HeaderValue, header::CONTENT_TYPE},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+use http_body_util::BodyExt;
+
+use crate::{
+    AppState,
+    config::{ProvenanceDelivery, TransformEndpoint},
+    middleware::RequestId,
+    services::provenance::{self, PROVENANCE_FIELD, PROVENANCE_HEADER, ProvenanceMetadata},
+};
+
+/// Attach signed provenance metadata to successful, non-streaming JSON
+/// responses from the completion endpoints.
+///
+/// Responses without generated text, such as tool-call-only answers, are
+/// passed through unsigned.
+pub async fn provenance_middleware(
+    State(state): State<AppState>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let config = &state.config.features.provenance;
+    let Some(key) = config.key() else {
+        return next.run(req).await;
+    };
+    let Some(endpoint) = TransformEndpoint::from_path(req.uri().path()) else {
+        return next.run(req).await;
+    };
+    let request_id = req
+        .extensions()
+        .get::<RequestId>()
+        .cloned()
+        .unwrap_or_default();
+
+    let response = next.run(req).await;
+    if !response.status().is_success() || !is_json(&response) {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match body.collect().await {
+        Ok(collected) => collected.to_bytes(),
+        Err(_) => return (parts, Body::empty()).into_response(),
+    };
+    let Ok(mut json) = serde_json::from_slice::<serde_json::Value>(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+    let Some(text) = provenance::output_text(endpoint, &json) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+
+    let model = parts
+        .headers
+        .get("X-Model")
+        .and_then(|v| v.to_str().ok())
+        .or_else(|| json.get("model").and_then(|m| m.as_str()))
+        .unwrap_or("unknown")
+        .to_string();
+    let metadata = ProvenanceMetadata::sign(key, request_id.as_str(), &model, &text, Utc::now());
+
+    if config.delivery.header()
+        && let Ok(value) = HeaderValue::from_str(&metadata.to_header())
+    {
+        parts.headers.insert(PROVENANCE_HEADER, value);
+    }
+    if config.delivery == ProvenanceDelivery::Header {
+        return Response::from_parts(parts, Body::from(bytes));
+    }
+
+    if let Some(object) = json.as_object_mut() {
+        object.insert(
+            PROVENANCE_FIELD.to_string(),
+            serde_json::to_value(&metadata).unwrap_or_default(),
+        );
+    }
+    let body = serde_json::to_vec(&json).unwrap_or_else(|_| bytes.to_vec());
+    parts.headers.remove(axum::http::header::CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(body))
+}
+
+fn is_json(response: &Response) -> bool {
+    response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.starts_with("application/json"))
+}
@@ -5,18 +5,29 @@
 
 use axum::{
     body::Body,
-    extract::Request,
-    http::header::CONTENT_TYPE,
+    extract::{Request, State},
+    http::{HeaderValue, StatusCode, header::CONTENT_TYPE},
     middleware::Next,
     response::{IntoResponse, Response},
 };
 use http_body_util::BodyExt;
+use serde_json::{Map, Value};
 
-use crate::middleware::RequestId;
+use crate::{
+    config::ErrorFormat,
+    middleware::RequestId,
+    providers::error::{ERROR_CATEGORY_HEADER, ErrorCategory},
+};
 
 /// Header name for the request ID.
 pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
 
+/// Content type of RFC 9457 problem details.
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// Prefix of the problem `type` URI, followed by the error category.
+const PROBLEM_TYPE_PREFIX: &str = "urn:hadrian:error:";
+
 /// Middleware that adds a request ID to each request.
 ///
 /// If the request already has an X-Request-Id header, it's used.
@@ -24,7 +35,13 @@ pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
 ///
 /// For error responses (4xx/5xx with JSON body), the request ID is also
 /// injected into the `error.request_id` field for correlation with logs.
-pub async fn request_id_middleware(mut req: Request, next: Next) -> Response {
+/// With [`ErrorFormat::ProblemJson`] the error is then rewritten as RFC 9457
+/// problem details.
+pub async fn request_id_middleware(
+    State(error_format): State<ErrorFormat>,
+    mut req: Request,
+    next: Next,
+) -> Response {
     // Check for existing request ID in headers
     let request_id = req
         .headers()
@@ -35,6 +52,7 @@ pub async fn request_id_middleware(mut req: Request, next: Next) -> Response {
 
     // Add to extensions for use by handlers and other middleware
     req.extensions_mut().insert(request_id.clone());
+    let path = req.uri().path().to_string();
 
     // Create a span with the request ID for structured logging
     let span = tracing::info_span!(
@@ -50,7 +68,7 @@ pub async fn request_id_middleware(mut req: Request, next: Next) -> Response {
     let response = next.run(req).await;
 
     // Inject request_id into error responses
-    let response = inject_request_id_into_error(response, &request_id).await;
+    let response = inject_request_id_into_error(response, &request_id, error_format, &path).await;
 
     // Add request ID to response headers
     let mut response = response;
@@ -66,7 +84,16 @@ pub async fn request_id_middleware(mut req: Request, next: Next) -> Response {
 /// For error responses (4xx/5xx status codes) with JSON content type,
 /// this function parses the body and adds the request_id to the
 /// `error.request_id` field if the response has an `error` object.
-async fn inject_request_id_into_error(response: Response, request_id: &RequestId) -> Response {
+/// Errors without an `error.category` get one classified from their status
+/// and code, so every error carries the taxonomy regardless of its source.
+/// With [`ErrorFormat::ProblemJson`], errors are converted by
+/// [`problem_details`] and `instance` is set to `path`.
+async fn inject_request_id_into_error(
+    response: Response,
+    request_id: &RequestId,
+    error_format: ErrorFormat,
+    path: &str,
+) -> Response {
     let status = response.status();
 
     // Only process error responses
@@ -86,7 +113,7 @@ async fn inject_request_id_into_error(response: Response, request_id: &RequestId
     }
 
     // Extract parts and body
-    let (parts, body) = response.into_parts();
+    let (mut parts, body) = response.into_parts();
 
     // Collect body bytes
     let bytes = match body.collect().await {
@@ -98,14 +125,33 @@ async fn inject_request_id_into_error(response: Response, request_id: &RequestId
     };
 
     // Try to parse and modify the JSON
-    let modified_bytes = match serde_json::from_slice::<serde_json::Value>(&bytes) {
+    let modified_bytes = match serde_json::from_slice::<Value>(&bytes) {
         Ok(mut json) => {
             // Inject request_id into error.request_id if error object exists
             if let Some(error) = json.get_mut("error").and_then(|e| e.as_object_mut()) {
                 error.insert(
                     "request_id".to_string(),
-                    serde_json::Value::String(request_id.0.clone()),
+                    Value::String(request_id.0.clone()),
                 );
+                if !error.contains_key("category") {
+                    let field = |name: &str| error.get(name).and_then(|v| v.as_str());
+                    let category = ErrorCategory::classify(
+                        status,
+                        field("code").unwrap_or_default(),
+                        field("message").unwrap_or_default(),
+                    );
+                    error.insert(
+                        "category".to_string(),
+                        Value::String(category.as_str().to_string()),
+                    );
+                }
+                if error_format == ErrorFormat::ProblemJson {
+                    let from_provider = parts.headers.contains_key(ERROR_CATEGORY_HEADER);
+                    json = problem_details(status, error, path, from_provider);
+                    parts
+                        .headers
+                        .insert(CONTENT_TYPE, HeaderValue::from_static(PROBLEM_JSON));
+                }
             }
             // Serialize back to bytes
             serde_json::to_vec(&json).unwrap_or_else(|_| bytes.to_vec())
@@ -120,6 +166,53 @@ async fn inject_request_id_into_error(response: Response, request_id: &RequestId
     Response::from_parts(parts, Body::from(modified_bytes))
 }
 
+/// Convert an OpenAI-style `error` object into RFC 9457 problem details.
+///
+/// `type` is derived from the error category, so it identifies the kind of
+/// failure whichever provider produced it. `code`, `param`, `category` and
+/// `request_id` are kept as extension members, and errors passed through
+/// from a provider also carry the provider's code as `provider_error_code`.
+fn problem_details(
+    status: StatusCode,
+    error: &Map<String, Value>,
+    instance: &str,
+    from_provider: bool,
+) -> Value {
+    let field = |name: &str| error.get(name).and_then(|v| v.as_str());
+    let category = field("category").unwrap_or("internal");
+
+    let mut problem = Map::new();
+    problem.insert(
+        "type".to_string(),
+        Value::String(format!("{PROBLEM_TYPE_PREFIX}{category}")),
+    );
+    problem.insert(
+        "title".to_string(),
+        Value::String(status.canonical_reason().unwrap_or("Error").to_string()),
+    );
+    problem.insert("status".to_string(), Value::from(status.as_u16()));
+    problem.insert(
+        "detail".to_string(),
+        Value::String(field("message").unwrap_or_default().to_string()),
+    );
+    problem.insert("instance".to_string(), Value::String(instance.to_string()));
+    for name in ["code", "param", "category", "request_id"] {
+        if let Some(value) = error.get(name).filter(|v| !v.is_null()) {
+            problem.insert(name.to_string(), value.clone());
+        }
+    }
+    if from_provider {
+        // OpenAI puts the specific code in `code` and falls back to `type`
+        if let Some(code) = field("code").or_else(|| field("type")) {
+            problem.insert(
+                "provider_error_code".to_string(),
+                Value::String(code.to_string()),
+            );
+        }
+    }
+    Value::Object(problem)
+}
+
 #[cfg(test)]
 mod tests {
     use axum::http::StatusCode;
@@ -159,7 +252,8 @@ mod tests {
             .unwrap();
 
         // Inject request_id
-        let modified = inject_request_id_into_error(response, &request_id).await;
+        let modified =
+            inject_request_id_into_error(response, &request_id, ErrorFormat::Openai, "/").await;
 
         // Verify status is preserved
         assert_eq!(modified.status(), StatusCode::BAD_REQUEST);
@@ -175,6 +269,104 @@ mod tests {
             Some("invalid_request_error")
         );
         assert_eq!(json["error"]["message"].as_str(), Some("Test error"));
+        assert_eq!(json["error"]["category"].as_str(), Some("invalid_request"));
+    }
+
+    #[tokio::test]
+    async fn test_inject_category_keeps_existing_category() {
+        let request_id = RequestId::from_string("test-req-123".to_string());
+
+        let classified = serde_json::json!({
+            "error": { "message": "Budget limit exceeded", "code": "budget_exceeded" }
+        });
+        let tagged = serde_json::json!({
+            "error": { "message": "Overloaded", "code": "overloaded_error", "category": "provider_unavailable" }
+        });
+
+        for (status, body, expected) in [
+            (StatusCode::PAYMENT_REQUIRED, classified, "budget_exceeded"),
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                tagged,
+                "provider_unavailable",
+            ),
+        ] {
+            let response = Response::builder()
+                .status(status)
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                .unwrap();
+            let modified =
+                inject_request_id_into_error(response, &request_id, ErrorFormat::Openai, "/").await;
+            let bytes = modified.into_body().collect().await.unwrap().to_bytes();
+            let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+            assert_eq!(json["error"]["category"].as_str(), Some(expected));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_problem_json_format() {
+        let request_id = RequestId::from_string("test-req-123".to_string());
+
+        let gateway_error = serde_json::json!({
+            "error": {
+                "type": "invalid_request_error",
+                "message": "Budget limit exceeded",
+                "param": null,
+                "code": "budget_exceeded"
+            }
+        });
+        let response = Response::builder()
+            .status(StatusCode::PAYMENT_REQUIRED)
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_vec(&gateway_error).unwrap()))
+            .unwrap();
+        let modified = inject_request_id_into_error(
+            response,
+            &request_id,
+            ErrorFormat::ProblemJson,
+            "/api/v1/chat/completions",
+        )
+        .await;
+        assert_eq!(modified.headers()[CONTENT_TYPE], PROBLEM_JSON);
+        let bytes = modified.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "type": "urn:hadrian:error:budget_exceeded",
+                "title": "Payment Required",
+                "status": 402,
+                "detail": "Budget limit exceeded",
+                "instance": "/api/v1/chat/completions",
+                "code": "budget_exceeded",
+                "category": "budget_exceeded",
+                "request_id": "test-req-123"
+            })
+        );
+
+        // Upstream errors keep the provider's code
+        let provider_error = serde_json::json!({
+            "error": {
+                "type": "rate_limit_error",
+                "message": "Slow down",
+                "code": "rate_limit_exceeded",
+                "category": "rate_limited"
+            }
+        });
+        let response = Response::builder()
+            .status(StatusCode::TOO_MANY_REQUESTS)
+            .header(CONTENT_TYPE, "application/json")
+            .header(ERROR_CATEGORY_HEADER, "rate_limited")
+            .body(Body::from(serde_json::to_vec(&provider_error).unwrap()))
+            .unwrap();
+        let modified =
+            inject_request_id_into_error(response, &request_id, ErrorFormat::ProblemJson, "/")
+                .await;
+        let bytes = modified.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(json["type"], "urn:hadrian:error:rate_limited");
+        assert_eq!(json["provider_error_code"], "rate_limit_exceeded");
     }
 
     #[tokio::test]
@@ -189,7 +381,8 @@ mod tests {
             .body(Body::from(serde_json::to_vec(&body).unwrap()))
             .unwrap();
 
-        let modified = inject_request_id_into_error(response, &request_id).await;
+        let modified =
+            inject_request_id_into_error(response, &request_id, ErrorFormat::Openai, "/").await;
 
         // Verify body is unchanged (no request_id added)
         let (_, body) = modified.into_parts();
@@ -211,7 +404,8 @@ mod tests {
             .body(Body::from("Bad Request"))
             .unwrap();
 
-        let modified = inject_request_id_into_error(response, &request_id).await;
+        let modified =
+            inject_request_id_into_error(response, &request_id, ErrorFormat::Openai, "/").await;
 
         // Verify body is unchanged
         let (_, body) = modified.into_parts();
@@ -231,7 +425,8 @@ mod tests {
             .body(Body::from(serde_json::to_vec(&body).unwrap()))
             .unwrap();
 
-        let modified = inject_request_id_into_error(response, &request_id).await;
+        let modified =
+            inject_request_id_into_error(response, &request_id, ErrorFormat::Openai, "/").await;
 
         // Verify body is unchanged (no "error" object to inject into)
         let (_, body) = modified.into_parts();
@@ -0,0 +1,217 @@
+//! Per-organization request policy enforcement.
+//!
+//! Evaluates the caller's org policies (see
+//! [`RequestPolicyRegistry`](crate::authz::RequestPolicyRegistry)) against
+//! the request body. Must run after [`api_middleware`](super::api::api_middleware)
+//! so the caller's organization is known, and before
+//! [`transforms_middleware`](super::transforms::transforms_middleware) so
+//! policies see the body the caller sent.
+
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{StatusCode, header::CONTENT_LENGTH},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::{
+    AppState,
+    auth::AuthenticatedRequest,
+    authz::{RequestPolicyDecision, RequestPolicySubject},
+    config::TransformEndpoint,
+    openapi::ErrorResponse,
+};
+
+/// Reject, allow or modify data-plane requests per the org's request
+/// policies.
+///
+/// The organization is the credential's, or else the session user's (see
+/// [`AuthenticatedRequest::effective_org_id`]). Callers without any
+/// organization have no policies and pass through, as do bodies that aren't
+/// JSON objects so the handler reports the parse error as usual.
+pub async fn request_policies_middleware(
+    State(state): State<AppState>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let Some(registry) = state.request_policies.clone() else {
+        return next.run(req).await;
+    };
+    let Some(endpoint) = TransformEndpoint::from_path(req.uri().path()) else {
+        return next.run(req).await;
+    };
+    let Some(subject) = req
+        .extensions()
+        .get::<AuthenticatedRequest>()
+        .and_then(policy_subject)
+    else {
+        return next.run(req).await;
+    };
+
+    let (mut parts, body) = req.into_parts();
+    let bytes = match axum::body::to_bytes(body, state.config.server.body_limit_bytes).await {
+        Ok(bytes) => bytes,
+        Err(_) => {
+            return error_response(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "payload_too_large",
+                "Request body too large".to_string(),
+            );
+        }
+    };
+    let mut json = match serde_json::from_slice::<serde_json::Value>(&bytes) {
+        Ok(json) if json.is_object() => json,
+        _ => {
+            return next
+                .run(Request::from_parts(parts, Body::from(bytes)))
+                .await;
+        }
+    };
+
+    let evaluation = match registry
+        .evaluate(&subject, endpoint.as_str(), &mut json)
+        .await
+    {
+        Ok(evaluation) => evaluation,
+        Err(e) => {
+            tracing::error!(org_id = %subject.org_id, error = %e, "Failed to load request policies");
+            return error_response(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "policy_unavailable",
+                "Request policies could not be loaded".to_string(),
+            );
+        }
+    };
+
+    if evaluation.decision == RequestPolicyDecision::Deny {
+        tracing::info!(
+            org_id = %subject.org_id,
+            policy = evaluation.policy.as_deref().unwrap_or_default(),
+            endpoint = endpoint.as_str(),
+            "Request denied by org request policy"
+        );
+        return error_response(
+            StatusCode::FORBIDDEN,
+            "request_policy_denied",
+            evaluation
+                .message
+                .unwrap_or_else(|| "Request denied by policy".to_string()),
+        );
+    }
+
+    if evaluation.modified_by.is_empty() {
+        return next
+            .run(Request::from_parts(parts, Body::from(bytes)))
+            .await;
+    }
+
+    tracing::debug!(
+        org_id = %subject.org_id,
+        policies = ?evaluation.modified_by,
+        "Request modified by org request policies"
+    );
+    let body = serde_json::to_vec(&json).unwrap_or_else(|_| bytes.to_vec());
+    parts.headers.remove(CONTENT_LENGTH);
+    next.run(Request::from_parts(parts, Body::from(body))).await
+}
+
+fn policy_subject(auth: &AuthenticatedRequest) -> Option<RequestPolicySubject> {
+    let api_key = auth.api_key();
+    Some(RequestPolicySubject {
+        org_id: auth.effective_org_id()?,
+        user_id: auth.user_id(),
+        api_key_id: api_key.map(|k| k.key.id),
+        team_id: api_key.and_then(|k| k.team_id),
+        project_id: auth.effective_project_id(),
+        service_account_id: api_key.and_then(|k| k.service_account_id),
+    })
+}
+
+fn error_response(status: StatusCode, code: &str, message: String) -> Response {
+    (status, axum::Json(ErrorResponse::new(code, message))).into_response()
+}
+
+#[cfg(all(test, feature = "database-sqlite"))]
+mod tests {
+    use axum::{Extension, Router, routing::post};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::{
+        auth::{Identity, IdentityKind},
+        models::{CreateOrgRequestPolicy, CreateOrganization},
+    };
+
+    #[tokio::test]
+    async fn test_policies_apply_to_session_identity() {
+        let config = crate::config::GatewayConfig::parse(
+            r#"
+[database]
+type = "sqlite"
+path = "file:request_policies_session_identity_db?mode=memory&cache=shared"
+create_if_missing = true
+run_migrations = true
+wal_mode = false
+busy_timeout_ms = 5000
+
+[providers.test]
+type = "test"
+"#,
+        )
+        .expect("Failed to parse test config");
+        let state = crate::AppState::new(config)
+            .await
+            .expect("Failed to create AppState");
+        let db = state.db.as_ref().unwrap();
+        let org = db
+            .organizations()
+            .create(CreateOrganization {
+                slug: "policy-session".to_string(),
+                name: "Policy Session".to_string(),
+            })
+            .await
+            .unwrap();
+        db.org_request_policies()
+            .create(
+                org.id,
+                CreateOrgRequestPolicy {
+                    name: "deny-all".to_string(),
+                    condition: "true".to_string(),
+                    message: Some("Blocked for this org".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        // A session user carries their org only through the identity
+        let auth = AuthenticatedRequest::new(IdentityKind::Identity(Identity {
+            external_id: "user-1".to_string(),
+            email: None,
+            name: None,
+            user_id: None,
+            roles: vec![],
+            idp_groups: vec![],
+            org_ids: vec![org.id.to_string()],
+            team_ids: vec![],
+            project_ids: vec![],
+        }));
+        let app = Router::new()
+            .route("/v1/chat/completions", post(|| async { "ok" }))
+            .layer(axum::middleware::from_fn_with_state(
+                state,
+                request_policies_middleware,
+            ))
+            .layer(Extension(auth));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/v1/chat/completions")
+            .header("content-type", "application/json")
+            .body(Body::from(r#"{"model": "test/test-model"}"#))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+}
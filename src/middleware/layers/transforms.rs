@@ -0,0 +1,332 @@
+//! Request/response transformation middleware.
+//!
+//! Runs the [`TransformPipeline`](crate::transforms::TransformPipeline) built
+//! from `[features.transforms]` around the data-plane endpoints. Must run
+//! after [`api_middleware`](super::api::api_middleware) so rules can see the
+//! caller's organization and project.
+
+use std::time::Duration;
+
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{StatusCode, header::CONTENT_TYPE},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use futures::StreamExt;
+use http_body_util::BodyExt;
+use uuid::Uuid;
+
+use crate::{
+    AppState,
+    auth::AuthenticatedRequest,
+    cache::CacheKeys,
+    config::TransformEndpoint,
+    openapi::ErrorResponse,
+    transforms::{
+        TransformContext, TransformError, TransformPipeline,
+        postprocess::{PostProcessor, SseRewriter},
+    },
+};
+
+/// How long organization slugs are cached for org-scoped rules.
+const ORG_SLUG_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Apply configured transforms to the request body, the response body and
+/// the response headers.
+///
+/// Bodies that aren't JSON objects are passed through untouched so the
+/// handler reports the parse error as usual. Response bodies are rewritten
+/// for successful JSON responses; successful streaming responses only go
+/// through the post-processing rules, event by event.
+pub async fn transforms_middleware(
+    State(state): State<AppState>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let Some(pipeline) = state.transforms.clone() else {
+        return next.run(req).await;
+    };
+    let Some(endpoint) = TransformEndpoint::from_path(req.uri().path()) else {
+        return next.run(req).await;
+    };
+    if !pipeline.targets(endpoint) {
+        return next.run(req).await;
+    }
+
+    let (mut parts, body) = req.into_parts();
+    let bytes = match axum::body::to_bytes(body, state.config.server.body_limit_bytes).await {
+        Ok(bytes) => bytes,
+        Err(_) => {
+            return transform_error_response(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "payload_too_large",
+                "Request body too large".to_string(),
+            );
+        }
+    };
+    let mut json = match serde_json::from_slice::<serde_json::Value>(&bytes) {
+        Ok(json) if json.is_object() => json,
+        _ => {
+            return next
+                .run(Request::from_parts(parts, Body::from(bytes)))
+                .await;
+        }
+    };
+
+    let auth = parts.extensions.get::<AuthenticatedRequest>();
+    let org_id = auth.and_then(|a| a.effective_org_id());
+    let org_slug = match org_id {
+        Some(org_id) if pipeline.filters_orgs() => match org_slug(&state, org_id).await {
+            Ok(slug) => slug,
+            Err(response) => return response,
+        },
+        _ => None,
+    };
+    let ctx = TransformContext {
+        endpoint,
+        model: json.get("model").and_then(|m| m.as_str()).map(String::from),
+        org_id,
+        org_slug,
+        project_id: auth.and_then(|a| a.effective_project_id()),
+    };
+
+    if let Err(e) = pipeline.transform_request(&ctx, &mut json).await {
+        tracing::error!(error = %e, endpoint = endpoint.as_str(), "Request transform failed");
+        return transform_failed(e);
+    }
+
+    let body = serde_json::to_vec(&json).unwrap_or_else(|_| bytes.to_vec());
+    parts.headers.remove(axum::http::header::CONTENT_LENGTH);
+    let mut response = next.run(Request::from_parts(parts, Body::from(body))).await;
+
+    if response.status().is_success() && is_json(&response) && pipeline.has_response_rules(&ctx) {
+        response = match transform_response_body(response, &pipeline, &ctx).await {
+            Ok(response) => response,
+            Err(e) => {
+                tracing::error!(error = %e, endpoint = endpoint.as_str(), "Response transform failed");
+                return transform_failed(e);
+            }
+        };
+    } else if response.status().is_success() && is_event_stream(&response) {
+        let steps = pipeline.stream_post_processors(&ctx);
+        if !steps.is_empty() {
+            response = rewrite_stream(response, endpoint, steps);
+        }
+    }
+
+    pipeline.apply_headers(&ctx, response.headers_mut());
+    response
+}
+
+fn is_json(response: &Response) -> bool {
+    response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.starts_with("application/json"))
+}
+
+fn is_event_stream(response: &Response) -> bool {
+    response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.starts_with("text/event-stream"))
+}
+
+/// Look up the caller's organization slug for org-scoped rules, consulting
+/// the cache first.
+///
+/// Lookup failures fail closed: a redaction rule must not silently lapse
+/// because the organization can't be read.
+async fn org_slug(state: &AppState, org_id: Uuid) -> Result<Option<String>, Response> {
+    let Some(db) = &state.db else {
+        return Ok(None);
+    };
+    let cache_key = CacheKeys::org_slug(org_id);
+    if let Some(cache) = &state.cache
+        && let Ok(Some(bytes)) = cache.get_bytes(&cache_key).await
+        && let Ok(slug) = String::from_utf8(bytes)
+    {
+        return Ok(Some(slug));
+    }
+
+    let org = db.organizations().get_by_id(org_id).await.map_err(|e| {
+        tracing::error!(error = %e, %org_id, "Failed to load organization for transforms");
+        transform_error_response(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "transforms_unavailable",
+            "Transform rules could not be matched to the organization".to_string(),
+        )
+    })?;
+    let Some(org) = org else {
+        return Ok(None);
+    };
+
+    if let Some(cache) = &state.cache {
+        let _ = cache
+            .set_bytes(&cache_key, org.slug.as_bytes(), ORG_SLUG_CACHE_TTL)
+            .await;
+    }
+    Ok(Some(org.slug))
+}
+
+/// Run a streamed response's text through the post-processing rules as it
+/// passes.
+fn rewrite_stream(
+    response: Response,
+    endpoint: TransformEndpoint,
+    steps: Vec<std::sync::Arc<PostProcessor>>,
+) -> Response {
+    let (mut parts, body) = response.into_parts();
+    let rewriter = SseRewriter::new(endpoint, steps);
+    let stream = futures::stream::unfold(
+        (body.into_data_stream(), Some(rewriter)),
+        |(mut upstream, mut rewriter)| async move {
+            let active = rewriter.as_mut()?;
+            match upstream.next().await {
+                Some(Ok(chunk)) => {
+                    let out = active.push(&chunk);
+                    Some((Ok(out), (upstream, rewriter)))
+                }
+                Some(Err(e)) => Some((Err(e), (upstream, None))),
+                None => {
+                    let out = active.finish();
+                    Some((Ok(out), (upstream, None)))
+                }
+            }
+        },
+    )
+    .filter(|chunk| std::future::ready(!matches!(chunk, Ok(bytes) if bytes.is_empty())));
+    parts.headers.remove(axum::http::header::CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from_stream(stream))
+}
+
+async fn transform_response_body(
+    response: Response,
+    pipeline: &TransformPipeline,
+    ctx: &TransformContext,
+) -> Result<Response, TransformError> {
+    let (mut parts, body) = response.into_parts();
+    let bytes = match body.collect().await {
+        Ok(collected) => collected.to_bytes(),
+        Err(_) => return Ok((parts, Body::empty()).into_response()),
+    };
+
+    let Ok(mut json) = serde_json::from_slice::<serde_json::Value>(&bytes) else {
+        return Ok(Response::from_parts(parts, Body::from(bytes)));
+    };
+    pipeline.transform_response(ctx, &mut json).await?;
+
+    let body = serde_json::to_vec(&json).unwrap_or_else(|_| bytes.to_vec());
+    parts.headers.remove(axum::http::header::CONTENT_LENGTH);
+    Ok(Response::from_parts(parts, Body::from(body)))
+}
+
+fn transform_failed(error: TransformError) -> Response {
+    transform_error_response(
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "transform_failed",
+        error.to_string(),
+    )
+}
+
+fn transform_error_response(status: StatusCode, code: &str, message: String) -> Response {
+    (status, axum::Json(ErrorResponse::new(code, message))).into_response()
+}
+
+#[cfg(all(test, feature = "database-sqlite"))]
+mod tests {
+    use axum::{Extension, Json, Router, routing::post};
+    use serde_json::{Value, json};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::{
+        auth::{Identity, IdentityKind},
+        models::CreateOrganization,
+    };
+
+    #[tokio::test]
+    async fn test_org_scoped_rules_apply_to_session_identity() {
+        let config = crate::config::GatewayConfig::parse(
+            r#"
+[database]
+type = "sqlite"
+path = "file:transforms_session_identity_db?mode=memory&cache=shared"
+create_if_missing = true
+run_migrations = true
+wal_mode = false
+busy_timeout_ms = 5000
+
+[providers.test]
+type = "test"
+
+[features.transforms]
+[[features.transforms.rules]]
+type = "redact"
+orgs = ["acme"]
+patterns = ["\\b\\d{16}\\b"]
+"#,
+        )
+        .expect("Failed to parse test config");
+        let state = crate::AppState::new(config)
+            .await
+            .expect("Failed to create AppState");
+        let org = state
+            .db
+            .as_ref()
+            .unwrap()
+            .organizations()
+            .create(CreateOrganization {
+                slug: "acme".to_string(),
+                name: "Acme".to_string(),
+            })
+            .await
+            .unwrap();
+
+        // A session user carries their org only through the identity
+        let auth = AuthenticatedRequest::new(IdentityKind::Identity(Identity {
+            external_id: "user-1".to_string(),
+            email: None,
+            name: None,
+            user_id: None,
+            roles: vec![],
+            idp_groups: vec![],
+            org_ids: vec![org.id.to_string()],
+            team_ids: vec![],
+            project_ids: vec![],
+        }));
+        let app = Router::new()
+            .route(
+                "/v1/chat/completions",
+                post(|| async {
+                    Json(json!({"choices": [{"message": {
+                        "role": "assistant",
+                        "content": "Card 4111111111111111"
+                    }}]}))
+                }),
+            )
+            .layer(axum::middleware::from_fn_with_state(
+                state,
+                transforms_middleware,
+            ))
+            .layer(Extension(auth));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/v1/chat/completions")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(r#"{"model": "test/test-model"}"#))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["choices"][0]["message"]["content"], "Card [REDACTED]");
+    }
+}
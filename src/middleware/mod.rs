@@ -10,9 +10,15 @@
 //!
 //! ## API routes (`/v1/*`)
 //! Applied via [`get_api_routes()`](crate::routes::api::get_api_routes) in this order:
+//! 0. [`drain_middleware`] — Rejects new requests while draining, tracks in-flight ones
 //! 1. [`rate_limit_middleware`] — IP-based rate limiting (rejects early before auth overhead)
 //! 2. [`api_middleware`] — Authentication, budget enforcement, usage tracking
 //! 3. [`api_authz_middleware`] — CEL-based authorization policy evaluation
+//! 4. [`idempotency_middleware`] — Replays responses for repeated `Idempotency-Key`s
+//! 5. [`request_policies_middleware`] — Per-org CEL request policies (allow/deny/modify)
+//! 6. [`parameter_governance_middleware`] — Per-org/project parameter bounds (clamp/reject)
+//! 7. [`provenance_middleware`] — Signed provenance metadata on completion responses
+//! 8. [`transforms_middleware`] — Request/response transformation hooks
 //!
 //! ## Admin routes (`/admin/v1/*`)
 //! - [`admin_auth_middleware`] — Admin authentication (OIDC/cookie/API key)
@@ -44,7 +50,13 @@ pub use layers::{
     admin::admin_auth_middleware,
     api::api_middleware,
     authz::{AuthzResponse, api_authz_middleware, authz_middleware, permissive_authz_middleware},
+    drain::{DrainState, DrainStatus, drain_middleware},
+    idempotency::idempotency_middleware,
+    parameter_governance::parameter_governance_middleware,
+    provenance::provenance_middleware,
     rate_limit::{discover_rate_limit_middleware, rate_limit_middleware},
     request_id::request_id_middleware,
+    request_policies::request_policies_middleware,
     security_headers::security_headers_middleware,
+    transforms::transforms_middleware,
 };
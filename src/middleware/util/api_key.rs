@@ -0,0 +1,84 @@
+//! Finding API keys by their raw value.
+//!
+//! Keys are stored under the hash scheme configured when they were created or
+//! last used. A lookup tries the configured scheme first, falls back to the
+//! legacy SHA-256 digest, and re-hashes keys found under an older scheme so
+//! each key migrates the first time it's used.
+
+use crate::{
+    db::{DbPool, DbResult},
+    models::{ApiKeyHasher, ApiKeyWithOwner},
+};
+
+/// An API key matched by its raw value.
+pub struct FoundApiKey {
+    pub key: ApiKeyWithOwner,
+    /// Digest the key is stored under, after any re-hash. Use it as the
+    /// key's cache key so bulk invalidation by stored hash reaches it.
+    pub key_hash: String,
+}
+
+/// Look up and verify a raw API key, re-hashing it if it is stored under an
+/// older scheme.
+///
+/// Revocation, expiry and grace periods are left to the caller. Argon2
+/// verification runs inline, so callers should cache the result.
+pub async fn find_api_key(
+    db: &DbPool,
+    hasher: &ApiKeyHasher,
+    raw_key: &str,
+) -> DbResult<Option<FoundApiKey>> {
+    let lookup_hash = hasher.lookup_hash(raw_key);
+    let (key, found_hash) = match db.api_keys().get_by_hash(&lookup_hash).await? {
+        Some(key) => (key, lookup_hash),
+        None => {
+            let Some(legacy_hash) = hasher.legacy_lookup_hash(raw_key) else {
+                return Ok(None);
+            };
+            match db.api_keys().get_by_hash(&legacy_hash).await? {
+                Some(key) => (key, legacy_hash),
+                None => return Ok(None),
+            }
+        }
+    };
+
+    if !hasher.verify(raw_key, &key.hash_scheme, key.key_verifier.as_deref()) {
+        return Ok(None);
+    }
+
+    if !hasher.needs_rehash(raw_key, &key.hash_scheme, &found_hash) {
+        return Ok(Some(FoundApiKey {
+            key,
+            key_hash: found_hash,
+        }));
+    }
+
+    let new_hash = hasher.hash(raw_key);
+    let key_hash = match db
+        .api_keys()
+        .update_hash(key.key.id, &found_hash, &new_hash)
+        .await
+    {
+        Ok(true) => {
+            tracing::info!(
+                api_key_id = %key.key.id,
+                from = %key.hash_scheme,
+                to = new_hash.scheme.as_str(),
+                "Re-hashed API key"
+            );
+            new_hash.key_hash
+        }
+        // A concurrent request re-hashed it first
+        Ok(false) => new_hash.key_hash,
+        Err(e) => {
+            tracing::warn!(
+                api_key_id = %key.key.id,
+                error = %e,
+                "Failed to re-hash API key; will retry on next use"
+            );
+            found_hash
+        }
+    };
+
+    Ok(Some(FoundApiKey { key, key_hash }))
+}
@@ -1,3 +1,6 @@
+pub mod api_key;
 pub mod budget;
 pub mod scope;
+pub mod smoothing;
+pub mod step_up;
 pub mod usage;
@@ -1,8 +1,34 @@
 //! API key scope enforcement.
 //!
-//! Maps request paths to required scopes for access control.
+//! Maps requests to required scopes for access control.
 
-use crate::models::ApiKeyScope;
+use axum::http::Method;
+
+use crate::models::{ADMIN_SCOPE_AREAS, ApiKeyScope, RequiredScope, ScopeAccess};
+
+/// Admin areas whose next path segment is a sub-resource rather than an ID.
+const SINGLETON_ADMIN_AREAS: &[&str] = &[
+    "access-reviews",
+    "email-log",
+    "entitlements",
+    "federation",
+    "labels",
+    "me",
+    "model-access",
+    "model-degradation",
+    "network-policy",
+    "observability",
+    "parameter-governance",
+    "provenance",
+    "reconciliation",
+    "scim-config",
+    "semantic-cache",
+    "sso-config",
+    "usage",
+];
+
+/// Route segments that belong to an area of a different name.
+const ADMIN_AREA_ALIASES: &[(&str, &str)] = &[("allowed-models", "model-access")];
 
 /// Determine the required scope for a given request path.
 ///
@@ -13,12 +39,12 @@ use crate::models::ApiKeyScope;
 ///
 /// | Scope | Endpoints |
 /// |-------|-----------|
-/// | `chat` | `/v1/chat/completions`, `/v1/responses` |
+/// | `chat` | `/v1/chat/completions`, `/v1/responses`, `/v1/agents/*`, `/v1/tokenize`, `/v1/estimate` |
 /// | `completions` | `/v1/completions` |
 /// | `embeddings` | `/v1/embeddings` |
 /// | `images` | `/v1/images/*` |
 /// | `audio` | `/v1/audio/*` |
-/// | `files` | `/v1/files/*`, `/v1/vector_stores/*` |
+/// | `files` | `/v1/files/*`, `/v1/vector_stores/*`, `/v1/fine_tuning/*` |
 /// | `models` | `/v1/models/*` |
 /// | `admin` | `/admin/*` |
 pub fn required_scope_for_path(path: &str) -> Option<ApiKeyScope> {
@@ -32,6 +58,17 @@ pub fn required_scope_for_path(path: &str) -> Option<ApiKeyScope> {
     if path.starts_with("/v1/responses") || path.starts_with("/api/v1/responses") {
         return Some(ApiKeyScope::Chat);
     }
+    // Agent runs are chat loops with tool calls
+    if path.starts_with("/v1/agents") || path.starts_with("/api/v1/agents") {
+        return Some(ApiKeyScope::Chat);
+    }
+    // Preflight endpoints need the scope of the requests they size
+    if matches!(
+        path,
+        "/v1/tokenize" | "/api/v1/tokenize" | "/v1/estimate" | "/api/v1/estimate"
+    ) {
+        return Some(ApiKeyScope::Chat);
+    }
 
     // Completions endpoint (legacy)
     if path == "/v1/completions" || path == "/api/v1/completions" {
@@ -60,6 +97,10 @@ pub fn required_scope_for_path(path: &str) -> Option<ApiKeyScope> {
     if path.starts_with("/v1/vector_stores") || path.starts_with("/api/v1/vector_stores") {
         return Some(ApiKeyScope::Files);
     }
+    // Fine-tuning jobs train on uploaded files
+    if path.starts_with("/v1/fine_tuning") || path.starts_with("/api/v1/fine_tuning") {
+        return Some(ApiKeyScope::Files);
+    }
 
     // Models endpoint
     if path.starts_with("/v1/models") || path.starts_with("/api/v1/models") {
@@ -75,6 +116,60 @@ pub fn required_scope_for_path(path: &str) -> Option<ApiKeyScope> {
     None
 }
 
+/// Determine the fine-grained scope a request needs.
+///
+/// Safe methods (`GET`, `HEAD`, `OPTIONS`) need `read` access, everything
+/// else needs `write`. Admin requests are additionally tagged with their area
+/// (see [`admin_scope_area`]).
+pub fn required_scope(method: &Method, path: &str) -> Option<RequiredScope> {
+    let scope = required_scope_for_path(path)?;
+    let access = if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) {
+        ScopeAccess::Read
+    } else {
+        ScopeAccess::Write
+    };
+    let area = match scope {
+        ApiKeyScope::Admin => admin_scope_area(path),
+        _ => None,
+    };
+    Some(RequiredScope::new(scope, access).with_area(area))
+}
+
+/// Resolve the admin area an `/admin/v1/...` path belongs to.
+///
+/// Nested routes belong to their innermost resource, so
+/// `/organizations/{org}/projects/{project}/usage` is `usage` and
+/// `/organizations/{org}/service-accounts/{sa}/client-certs` is
+/// `service-accounts`. Self-service routes under `/me` are always `me`, and
+/// `allowed-models` belongs to `model-access`.
+/// Returns `None` for paths that don't match a known area.
+pub fn admin_scope_area(path: &str) -> Option<&'static str> {
+    let path = path.split('?').next().unwrap_or(path);
+    let rest = path.strip_prefix("/admin/v1/")?;
+
+    let mut area = None;
+    let mut expect_id = false;
+    for segment in rest.split('/').filter(|s| !s.is_empty()) {
+        if expect_id {
+            expect_id = false;
+            continue;
+        }
+        let segment = ADMIN_AREA_ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == segment)
+            .map_or(segment, |&(_, area)| area);
+        let Some(&known) = ADMIN_SCOPE_AREAS.iter().find(|a| **a == segment) else {
+            continue;
+        };
+        if known == "me" && area.is_none() {
+            return Some(known);
+        }
+        area = Some(known);
+        expect_id = !SINGLETON_ADMIN_AREAS.contains(&known);
+    }
+    area
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -97,6 +192,30 @@ mod tests {
             required_scope_for_path("/api/v1/responses"),
             Some(ApiKeyScope::Chat)
         );
+        assert_eq!(
+            required_scope_for_path("/v1/tokenize"),
+            Some(ApiKeyScope::Chat)
+        );
+        assert_eq!(
+            required_scope_for_path("/api/v1/tokenize"),
+            Some(ApiKeyScope::Chat)
+        );
+        assert_eq!(
+            required_scope_for_path("/v1/estimate"),
+            Some(ApiKeyScope::Chat)
+        );
+        assert_eq!(
+            required_scope_for_path("/api/v1/estimate?dry_run=true"),
+            Some(ApiKeyScope::Chat)
+        );
+        assert_eq!(
+            required_scope_for_path("/v1/agents/agent-1/runs"),
+            Some(ApiKeyScope::Chat)
+        );
+        assert_eq!(
+            required_scope_for_path("/api/v1/agents/agent-1/runs/run-1"),
+            Some(ApiKeyScope::Chat)
+        );
     }
 
     #[test]
@@ -173,6 +292,14 @@ mod tests {
             required_scope_for_path("/api/v1/vector_stores/vs-123/files"),
             Some(ApiKeyScope::Files)
         );
+        assert_eq!(
+            required_scope_for_path("/v1/fine_tuning/jobs"),
+            Some(ApiKeyScope::Files)
+        );
+        assert_eq!(
+            required_scope_for_path("/api/v1/fine_tuning/jobs/ftjob-1/cancel"),
+            Some(ApiKeyScope::Files)
+        );
     }
 
     #[test]
@@ -231,4 +358,165 @@ mod tests {
             Some(ApiKeyScope::Models)
         );
     }
+
+    #[test]
+    fn test_required_scope_access() {
+        let scope = required_scope(&Method::POST, "/v1/chat/completions").unwrap();
+        assert_eq!(scope.to_string(), "chat:write");
+
+        let scope = required_scope(&Method::GET, "/v1/files/file-123").unwrap();
+        assert_eq!(scope.to_string(), "files:read");
+
+        let scope = required_scope(&Method::DELETE, "/v1/files/file-123").unwrap();
+        assert_eq!(scope.to_string(), "files:write");
+
+        assert!(required_scope(&Method::GET, "/health").is_none());
+    }
+
+    #[test]
+    fn test_required_scope_admin() {
+        let scope = required_scope(&Method::GET, "/admin/v1/organizations/acme/usage").unwrap();
+        assert_eq!(scope.to_string(), "admin:usage:read");
+
+        let scope = required_scope(&Method::POST, "/admin/v1/users").unwrap();
+        assert_eq!(scope.to_string(), "admin:users:write");
+
+        let scope = required_scope(&Method::GET, "/admin/v1/session-info").unwrap();
+        assert_eq!(scope.to_string(), "admin:read");
+    }
+
+    #[test]
+    fn test_admin_scope_area() {
+        let cases = [
+            ("/admin/v1/organizations", Some("organizations")),
+            ("/admin/v1/organizations/acme", Some("organizations")),
+            (
+                "/admin/v1/organizations/acme/access-report",
+                Some("organizations"),
+            ),
+            (
+                "/admin/v1/organizations/acme/projects/web",
+                Some("projects"),
+            ),
+            (
+                "/admin/v1/organizations/acme/projects/web/usage/by-date",
+                Some("usage"),
+            ),
+            (
+                "/admin/v1/organizations/acme/teams/core/members/123",
+                Some("members"),
+            ),
+            (
+                "/admin/v1/organizations/acme/service-accounts/bot/api-keys",
+                Some("api-keys"),
+            ),
+            (
+                "/admin/v1/organizations/acme/service-accounts/bot/client-certs",
+                Some("service-accounts"),
+            ),
+            (
+                "/admin/v1/organizations/acme/sso-config/domains/1/verify",
+                Some("sso-config"),
+            ),
+            ("/admin/v1/api-keys/123/usage", Some("usage")),
+            ("/admin/v1/providers/openai/usage", Some("usage")),
+            ("/admin/v1/providers/health", Some("providers")),
+            ("/admin/v1/usage/logs?limit=5", Some("usage")),
+            ("/admin/v1/me/api-keys", Some("me")),
+            ("/admin/v1/me/usage", Some("me")),
+            ("/admin/v1/federation/gateways/eu-1", Some("federation")),
+            (
+                "/admin/v1/reconciliation/imports/csv",
+                Some("reconciliation"),
+            ),
+            (
+                "/admin/v1/organizations/acme/responses/resp_1/replay",
+                Some("responses"),
+            ),
+            (
+                "/admin/v1/organizations/acme/semantic-cache",
+                Some("semantic-cache"),
+            ),
+            (
+                "/admin/v1/organizations/acme/network-policy",
+                Some("network-policy"),
+            ),
+            (
+                "/admin/v1/organizations/acme/model-access",
+                Some("model-access"),
+            ),
+            (
+                "/admin/v1/organizations/acme/projects/web/model-access",
+                Some("model-access"),
+            ),
+            (
+                "/admin/v1/organizations/acme/allowed-models?project=web",
+                Some("model-access"),
+            ),
+            (
+                "/admin/v1/organizations/acme/invitations",
+                Some("invitations"),
+            ),
+            (
+                "/admin/v1/organizations/acme/invitations/123/resend",
+                Some("invitations"),
+            ),
+            (
+                "/admin/v1/organizations/acme/elevations",
+                Some("elevations"),
+            ),
+            (
+                "/admin/v1/organizations/acme/elevations/123/approve",
+                Some("elevations"),
+            ),
+            (
+                "/admin/v1/organizations/acme/entitlements",
+                Some("entitlements"),
+            ),
+            ("/admin/v1/organizations/acme/labels", Some("labels")),
+            (
+                "/admin/v1/organizations/acme/projects/web/labels",
+                Some("labels"),
+            ),
+            ("/admin/v1/api-keys/123/labels", Some("labels")),
+            ("/admin/v1/templates/123/labels", Some("labels")),
+            (
+                "/admin/v1/organizations/acme/parameter-governance",
+                Some("parameter-governance"),
+            ),
+            (
+                "/admin/v1/organizations/acme/projects/web/parameter-governance",
+                Some("parameter-governance"),
+            ),
+            (
+                "/admin/v1/organizations/acme/model-degradation",
+                Some("model-degradation"),
+            ),
+            ("/admin/v1/organizations/acme/agents", Some("agents")),
+            ("/admin/v1/organizations/acme/agents/123", Some("agents")),
+            (
+                "/admin/v1/organizations/acme/scheduled-tasks",
+                Some("scheduled-tasks"),
+            ),
+            (
+                "/admin/v1/organizations/acme/scheduled-tasks/123/runs",
+                Some("scheduled-tasks"),
+            ),
+            (
+                "/admin/v1/organizations/acme/http-tools",
+                Some("http-tools"),
+            ),
+            (
+                "/admin/v1/organizations/acme/http-tools/123",
+                Some("http-tools"),
+            ),
+            // An ID that happens to look like an area doesn't count
+            ("/admin/v1/organizations/usage/teams", Some("teams")),
+            ("/admin/v1/ui/config", None),
+            ("/v1/chat/completions", None),
+        ];
+        for (path, expected) in cases {
+            assert_eq!(admin_scope_area(path), expected, "{path}");
+        }
+    }
 }
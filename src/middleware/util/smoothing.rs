@@ -0,0 +1,167 @@
+//! Per-API-key burst smoothing.
+//!
+//! A token bucket per key, kept as a theoretical arrival time (GCRA): each
+//! admitted request pushes the key's arrival time forward by one emission
+//! interval (`60s / rpm`), and a request may start once the arrival time is
+//! within `burst` intervals of now. Requests further ahead than that wait
+//! until their turn if the wait fits in `max_delay`, so a burst is spread out
+//! at the key's sustained rate instead of being answered with 429s.
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use uuid::Uuid;
+
+use crate::{compat::Mutex, config::RateLimitSmoothingConfig};
+
+/// Buckets are pruned once this many keys are tracked. Pruning only drops
+/// buckets that have fully refilled, which are equivalent to fresh ones.
+const PRUNE_THRESHOLD: usize = 10_000;
+
+/// Whether a request may proceed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Admission {
+    /// A token was available.
+    Now,
+    /// The request holds the next token but must wait this long for it.
+    After(Duration),
+    /// The wait would exceed the configured maximum.
+    Rejected { retry_after: Duration },
+}
+
+/// Token buckets for every API key seen on this node.
+#[derive(Debug)]
+pub struct RequestSmoother {
+    burst: u32,
+    max_delay: Duration,
+    /// Theoretical arrival time per key.
+    buckets: Mutex<HashMap<Uuid, Instant>>,
+}
+
+impl RequestSmoother {
+    pub fn new(config: &RateLimitSmoothingConfig) -> Self {
+        Self {
+            burst: config.burst.max(1),
+            max_delay: Duration::from_millis(config.max_delay_ms),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Build a smoother if smoothing is enabled.
+    pub fn from_config(config: &RateLimitSmoothingConfig) -> Option<Self> {
+        config.enabled.then(|| Self::new(config))
+    }
+
+    /// Take a token from `key`'s bucket, which refills at `rpm` per minute.
+    ///
+    /// A request admitted with [`Admission::After`] has already reserved its
+    /// token and must wait before proceeding.
+    pub fn admit(&self, key: Uuid, rpm: u32, now: Instant) -> Admission {
+        if rpm == 0 {
+            return Admission::Now;
+        }
+        let interval = Duration::from_secs(60) / rpm;
+        let tolerance = interval * (self.burst - 1);
+
+        let mut buckets = self.buckets.lock();
+        if buckets.len() >= PRUNE_THRESHOLD {
+            buckets.retain(|_, tat| *tat > now);
+        }
+        let tat = buckets.get(&key).copied().unwrap_or(now).max(now);
+        let wait = tat
+            .checked_sub(tolerance)
+            .map(|start| start.saturating_duration_since(now))
+            .unwrap_or_default();
+        if wait > self.max_delay {
+            return Admission::Rejected {
+                retry_after: wait - self.max_delay,
+            };
+        }
+        buckets.insert(key, tat + interval);
+
+        if wait.is_zero() {
+            Admission::Now
+        } else {
+            Admission::After(wait)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn smoother(burst: u32, max_delay_ms: u64) -> RequestSmoother {
+        RequestSmoother::new(&RateLimitSmoothingConfig {
+            enabled: true,
+            burst,
+            max_delay_ms,
+        })
+    }
+
+    #[test]
+    fn test_burst_passes_then_excess_is_paced() {
+        // 60 rpm = one token per second
+        let smoother = smoother(3, 2500);
+        let key = Uuid::new_v4();
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            assert_eq!(smoother.admit(key, 60, now), Admission::Now);
+        }
+        assert_eq!(
+            smoother.admit(key, 60, now),
+            Admission::After(Duration::from_secs(1))
+        );
+        assert_eq!(
+            smoother.admit(key, 60, now),
+            Admission::After(Duration::from_secs(2))
+        );
+        assert_eq!(
+            smoother.admit(key, 60, now),
+            Admission::Rejected {
+                retry_after: Duration::from_millis(500)
+            }
+        );
+
+        // Other keys have their own bucket
+        assert_eq!(smoother.admit(Uuid::new_v4(), 60, now), Admission::Now);
+    }
+
+    #[test]
+    fn test_bucket_refills_over_time() {
+        let smoother = smoother(2, 0);
+        let key = Uuid::new_v4();
+        let now = Instant::now();
+
+        assert_eq!(smoother.admit(key, 60, now), Admission::Now);
+        assert_eq!(smoother.admit(key, 60, now), Admission::Now);
+        assert!(matches!(
+            smoother.admit(key, 60, now),
+            Admission::Rejected { .. }
+        ));
+
+        // One token back after one interval, the full burst after two
+        let later = now + Duration::from_secs(1);
+        assert_eq!(smoother.admit(key, 60, later), Admission::Now);
+        assert!(matches!(
+            smoother.admit(key, 60, later),
+            Admission::Rejected { .. }
+        ));
+        let much_later = now + Duration::from_secs(10);
+        assert_eq!(smoother.admit(key, 60, much_later), Admission::Now);
+        assert_eq!(smoother.admit(key, 60, much_later), Admission::Now);
+    }
+
+    #[test]
+    fn test_zero_rpm_is_not_smoothed() {
+        let smoother = smoother(1, 0);
+        let key = Uuid::new_v4();
+        let now = Instant::now();
+        for _ in 0..5 {
+            assert_eq!(smoother.admit(key, 0, now), Admission::Now);
+        }
+    }
+}
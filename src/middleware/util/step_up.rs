@@ -0,0 +1,326 @@
+//! Step-up authentication policy for high-risk admin routes.
+//!
+//! [`requires_recent_auth`] decides whether a request is protected by
+//! `[auth.step_up].routes`. The cache helpers remember successful TOTP
+//! verifications and throttle failed codes per user. Without a configured
+//! cache, failures are throttled in process memory instead.
+
+use std::{sync::OnceLock, time::Duration};
+
+use axum::http::Method;
+use chrono::{DateTime, TimeZone, Utc};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    AppState,
+    auth::Identity,
+    cache::{Cache, CacheKeys, MemoryCache},
+    config::MemoryCacheConfig,
+    db::DbError,
+    middleware::ClientInfo,
+    models::{AuditActorType, CreateAuditLog},
+    services::{Services, StepUpError, audit_logs::auth_events},
+};
+
+/// Header carrying an inline TOTP code for a single protected request.
+pub const STEP_UP_TOTP_HEADER: &str = "x-step-up-totp";
+
+/// Whether `method path` matches one of the configured step-up route
+/// patterns (`"METHOD /admin/v1/path/{param}"`).
+pub fn requires_recent_auth(method: &Method, path: &str, routes: &[String]) -> bool {
+    let path = path.split('?').next().unwrap_or(path);
+    routes.iter().any(|route| {
+        route.split_once(' ').is_some_and(|(m, pattern)| {
+            m.eq_ignore_ascii_case(method.as_str()) && path_matches(pattern.trim(), path)
+        })
+    })
+}
+
+/// Segment-wise match where `{name}` matches any single non-empty segment.
+/// Trailing slashes are ignored on both sides.
+fn path_matches(pattern: &str, path: &str) -> bool {
+    let mut pattern = pattern.trim_end_matches('/').split('/');
+    let mut path = path.trim_end_matches('/').split('/');
+    loop {
+        match (pattern.next(), path.next()) {
+            (None, None) => return true,
+            (Some(p), Some(s)) if p.starts_with('{') && p.ends_with('}') => {
+                if s.is_empty() {
+                    return false;
+                }
+            }
+            (Some(p), Some(s)) if p == s => {}
+            _ => return false,
+        }
+    }
+}
+
+/// When the user's last TOTP verification stops satisfying step-up, if it
+/// still does.
+pub async fn verified_until(cache: &dyn Cache, user_id: Uuid) -> Option<DateTime<Utc>> {
+    let bytes = cache
+        .get_bytes(&CacheKeys::step_up_verified(user_id))
+        .await
+        .ok()??;
+    let secs: i64 = std::str::from_utf8(&bytes).ok()?.parse().ok()?;
+    Utc.timestamp_opt(secs, 0)
+        .single()
+        .filter(|until| *until > Utc::now())
+}
+
+/// Remember a successful TOTP verification for `ttl_secs` and reset the
+/// user's failure count. Returns the expiry.
+pub async fn mark_verified(cache: &dyn Cache, user_id: Uuid, ttl_secs: u64) -> DateTime<Utc> {
+    let until = Utc::now() + chrono::Duration::seconds(ttl_secs as i64);
+    let _ = cache
+        .set_bytes(
+            &CacheKeys::step_up_verified(user_id),
+            until.timestamp().to_string().as_bytes(),
+            Duration::from_secs(ttl_secs),
+        )
+        .await;
+    clear_failures(cache, user_id).await;
+    until
+}
+
+/// Reset the user's failed TOTP count.
+pub async fn clear_failures(cache: &dyn Cache, user_id: Uuid) {
+    let _ = cache.delete(&CacheKeys::step_up_failures(user_id)).await;
+}
+
+/// Drop any remembered verification, e.g. after the authenticator is removed.
+pub async fn clear_verified(cache: &dyn Cache, user_id: Uuid) {
+    let _ = cache.delete(&CacheKeys::step_up_verified(user_id)).await;
+}
+
+/// Where failure counts and lockouts are kept: the configured cache, or a
+/// process-local store when there is none, so the lockout always applies.
+fn lockout_cache(state: &AppState) -> &dyn Cache {
+    static LOCAL: OnceLock<MemoryCache> = OnceLock::new();
+    match state.cache.as_deref() {
+        Some(cache) => cache,
+        None => LOCAL.get_or_init(|| MemoryCache::new(&MemoryCacheConfig::default())),
+    }
+}
+
+/// Whether the user is locked out of TOTP attempts.
+pub async fn is_locked_out(cache: &dyn Cache, user_id: Uuid) -> bool {
+    matches!(
+        cache.get_bytes(&CacheKeys::step_up_lockout(user_id)).await,
+        Ok(Some(_))
+    )
+}
+
+/// Count a failed TOTP code. Once `max_failures` is reached the user is
+/// locked out for `window_secs`. Returns true if this failure triggered it.
+pub async fn record_failure(
+    cache: &dyn Cache,
+    user_id: Uuid,
+    max_failures: u32,
+    window_secs: u64,
+) -> bool {
+    let window = Duration::from_secs(window_secs);
+    let failures_key = CacheKeys::step_up_failures(user_id);
+    let count = cache.incr(&failures_key, window).await.unwrap_or(1);
+    if count < i64::from(max_failures) {
+        return false;
+    }
+    let _ = cache
+        .set_bytes(&CacheKeys::step_up_lockout(user_id), b"1", window)
+        .await;
+    let _ = cache.delete(&failures_key).await;
+    true
+}
+
+/// Outcome of checking a TOTP code for step-up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TotpAttempt {
+    /// Code accepted. Carries when the verification stops satisfying
+    /// step-up, if a cache is available to remember it.
+    Verified(Option<DateTime<Utc>>),
+    /// Wrong, malformed, or already used code
+    Invalid,
+    /// The user has no confirmed authenticator
+    NotEnrolled,
+    /// Too many recent failures
+    LockedOut,
+}
+
+/// Check a TOTP code for `user_id`, applying the failure lockout, recording
+/// the verification in the cache, and auditing the attempt.
+///
+/// Shared by `POST /admin/v1/me/step-up/verify` and the inline
+/// [`STEP_UP_TOTP_HEADER`] on protected routes.
+pub async fn attempt_totp(
+    state: &AppState,
+    services: &Services,
+    identity: &Identity,
+    user_id: Uuid,
+    code: &str,
+    client_info: &ClientInfo,
+) -> Result<TotpAttempt, DbError> {
+    let config = &state.config.auth.step_up;
+    let cache = state.cache.as_deref();
+    let lockout = lockout_cache(state);
+
+    if is_locked_out(lockout, user_id).await {
+        return Ok(TotpAttempt::LockedOut);
+    }
+
+    let outcome = match services.step_up.verify(user_id, code).await {
+        Ok(()) => {
+            let until = match cache {
+                Some(cache) => {
+                    Some(mark_verified(cache, user_id, config.verification_ttl_secs).await)
+                }
+                None => {
+                    clear_failures(lockout, user_id).await;
+                    None
+                }
+            };
+            TotpAttempt::Verified(until)
+        }
+        Err(StepUpError::InvalidCode) => {
+            if record_failure(
+                lockout,
+                user_id,
+                config.max_failed_attempts,
+                config.verification_ttl_secs,
+            )
+            .await
+            {
+                tracing::warn!(
+                    user_id = %user_id,
+                    event = "step_up.lockout_triggered",
+                    "Step-up TOTP lockout triggered after repeated failures"
+                );
+            }
+            TotpAttempt::Invalid
+        }
+        Err(StepUpError::NotEnrolled | StepUpError::AlreadyEnrolled) => {
+            return Ok(TotpAttempt::NotEnrolled);
+        }
+        Err(StepUpError::Database(e)) => return Err(e),
+    };
+
+    let action = match outcome {
+        TotpAttempt::Verified(_) => auth_events::STEP_UP_VERIFIED,
+        _ => auth_events::STEP_UP_FAILED,
+    };
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: AuditActorType::User,
+            actor_id: Some(user_id),
+            action: action.to_string(),
+            resource_type: "user".to_string(),
+            resource_id: user_id,
+            org_id: None,
+            project_id: None,
+            details: json!({
+                "method": "totp",
+                "external_id": identity.external_id,
+            }),
+            ip_address: client_info.ip_address.clone(),
+            user_agent: client_info.user_agent.clone(),
+        })
+        .await;
+
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{
+        cache::MemoryCache,
+        config::{MemoryCacheConfig, StepUpConfig},
+    };
+
+    #[test]
+    fn test_requires_recent_auth_matches_default_routes() {
+        let routes = StepUpConfig::default().routes;
+        let cases = [
+            (Method::DELETE, "/admin/v1/organizations/acme", true),
+            (Method::DELETE, "/admin/v1/organizations/acme/", true),
+            (Method::GET, "/admin/v1/organizations/acme", false),
+            (Method::PATCH, "/admin/v1/organizations/acme", false),
+            (
+                Method::DELETE,
+                "/admin/v1/organizations/acme/projects/p1",
+                false,
+            ),
+            (
+                Method::POST,
+                "/admin/v1/organizations/acme/sso-config",
+                true,
+            ),
+            (
+                Method::GET,
+                "/admin/v1/organizations/acme/sso-config",
+                false,
+            ),
+            (
+                Method::DELETE,
+                "/admin/v1/organizations/acme/scim-config",
+                true,
+            ),
+            (
+                Method::POST,
+                "/admin/v1/organizations/acme/scim-config/rotate-token",
+                true,
+            ),
+            (Method::DELETE, "/admin/v1/me/step-up/totp", true),
+            (Method::POST, "/admin/v1/me/step-up/totp/enroll", false),
+            (Method::DELETE, "/admin/v1/organizations//", false),
+        ];
+        for (method, path, expected) in cases {
+            assert_eq!(
+                requires_recent_auth(&method, path, &routes),
+                expected,
+                "{method} {path}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_requires_recent_auth_ignores_query_and_case() {
+        let routes = vec!["delete /admin/v1/teams/{id}".to_string()];
+        assert!(requires_recent_auth(
+            &Method::DELETE,
+            "/admin/v1/teams/t1?force=true",
+            &routes
+        ));
+        assert!(!requires_recent_auth(
+            &Method::DELETE,
+            "/admin/v1/teams",
+            &routes
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_verification_marker_and_lockout() {
+        let cache: Arc<dyn Cache> = Arc::new(MemoryCache::new(&MemoryCacheConfig::default()));
+        let user_id = Uuid::new_v4();
+
+        assert!(verified_until(cache.as_ref(), user_id).await.is_none());
+        let until = mark_verified(cache.as_ref(), user_id, 600).await;
+        assert_eq!(
+            verified_until(cache.as_ref(), user_id)
+                .await
+                .map(|t| t.timestamp()),
+            Some(until.timestamp())
+        );
+        clear_verified(cache.as_ref(), user_id).await;
+        assert!(verified_until(cache.as_ref(), user_id).await.is_none());
+
+        assert!(!record_failure(cache.as_ref(), user_id, 3, 600).await);
+        assert!(!record_failure(cache.as_ref(), user_id, 3, 600).await);
+        assert!(!is_locked_out(cache.as_ref(), user_id).await);
+        assert!(record_failure(cache.as_ref(), user_id, 3, 600).await);
+        assert!(is_locked_out(cache.as_ref(), user_id).await);
+    }
+}
@@ -1,4 +1,4 @@
-use std::time::Instant;
+use std::{collections::BTreeMap, time::Instant};
 
 use axum::response::Response;
 
@@ -13,6 +13,10 @@ pub struct UsageTracker {
     pub streamed: bool,
     /// Whether the provider is "static" (config) or "dynamic" (DB)
     pub provider_source: Option<String>,
+    /// `Idempotency-Key` header sent by the client
+    pub idempotency_key: Option<String>,
+    /// Cost allocation tags from the `X-Hadrian-Tags` header
+    pub tags: Option<BTreeMap<String, String>>,
 }
 
 impl UsageTracker {
@@ -24,6 +28,8 @@ impl UsageTracker {
             referer: None,
             streamed: false,
             provider_source: None,
+            idempotency_key: None,
+            tags: None,
         }
     }
 
@@ -138,6 +144,8 @@ pub fn tracker_from_headers(headers: &axum::http::HeaderMap) -> UsageTracker {
     if let Some(referer) = referer {
         tracker = tracker.with_referer(referer);
     }
+    tracker.idempotency_key = crate::services::idempotency::key_from_headers(headers);
+    tracker.tags = crate::services::usage_tags::from_headers(headers);
     tracker
 }
 
@@ -4,6 +4,8 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::openapi::PaginationMeta;
+
 /// Export format for access review reports
 #[derive(Debug, Clone, Copy, Default, Deserialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
@@ -111,6 +113,8 @@ pub struct AccessInventoryResponse {
     pub users: Vec<UserAccessInventoryEntry>,
     /// Summary statistics
     pub summary: AccessInventorySummary,
+    /// Pagination metadata
+    pub pagination: PaginationMeta,
 }
 
 /// Summary statistics for the access inventory
@@ -140,8 +144,16 @@ pub struct AccessInventoryQuery {
     /// Maximum number of users to return (default: 100, max: 1000)
     #[cfg_attr(feature = "utoipa", param(default = 100, maximum = 1000))]
     pub limit: Option<i64>,
-    /// Offset for pagination
-    #[cfg_attr(feature = "utoipa", param(default = 0))]
+    /// Cursor for keyset pagination, taken from `pagination.next_cursor` or
+    /// `pagination.prev_cursor` of a previous response.
+    #[cfg_attr(feature = "utoipa", param(nullable))]
+    pub cursor: Option<String>,
+    /// Pagination direction: "forward" (default) or "backward".
+    #[cfg_attr(feature = "utoipa", param(nullable))]
+    pub direction: Option<String>,
+    /// Number of users to skip (deprecated, use `cursor` instead). Ignored when
+    /// `cursor` is set.
+    #[cfg_attr(feature = "utoipa", param(default = 0, deprecated))]
     pub offset: Option<i64>,
     /// Export format (json or csv)
     #[cfg_attr(feature = "utoipa", param(default = "json"))]
@@ -0,0 +1,32 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Maximum length of a passkey's display name.
+pub const MAX_PASSKEY_NAME_LENGTH: usize = 64;
+
+/// A WebAuthn credential registered by an admin user.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct AdminPasskey {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    /// Base64url credential ID chosen by the authenticator
+    pub credential_id: String,
+    /// Display name, e.g. "YubiKey" or "MacBook Touch ID"
+    pub name: String,
+    /// Serialized public key and signature counter
+    #[serde(skip_serializing)]
+    pub credential: String,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Input for storing a newly registered passkey.
+#[derive(Debug, Clone)]
+pub struct CreateAdminPasskey {
+    pub user_id: Uuid,
+    pub credential_id: String,
+    pub name: String,
+    pub credential: String,
+}
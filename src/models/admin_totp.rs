@@ -0,0 +1,35 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// A user's TOTP authenticator for admin step-up authentication.
+#[derive(Clone)]
+pub struct AdminTotpEnrollment {
+    pub user_id: Uuid,
+    /// Base32 shared secret, decrypted by the service layer
+    pub secret: String,
+    /// When enrollment was confirmed with a valid code; `None` while pending
+    pub verified_at: Option<DateTime<Utc>>,
+    /// Highest time step accepted so far, used to reject replayed codes
+    pub last_used_step: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl AdminTotpEnrollment {
+    pub fn is_verified(&self) -> bool {
+        self.verified_at.is_some()
+    }
+}
+
+impl std::fmt::Debug for AdminTotpEnrollment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AdminTotpEnrollment")
+            .field("user_id", &self.user_id)
+            .field("secret", &"[REDACTED]")
+            .field("verified_at", &self.verified_at)
+            .field("last_used_step", &self.last_used_step)
+            .field("created_at", &self.created_at)
+            .field("updated_at", &self.updated_at)
+            .finish()
+    }
+}
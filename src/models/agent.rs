@@ -0,0 +1,227 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::api_types::responses::ResponsesToolDefinition;
+
+/// An agent defined through the admin API: a model with a system prompt and
+/// the server-executed tools it may call. Agents are run with
+/// `POST /v1/agents/{agent_id}/runs`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct Agent {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    /// Name of the agent (unique per organization)
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Model the agent runs on, as a client would request it
+    pub model: String,
+    /// System prompt
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+    /// Server-executed tools the agent may call
+    pub tools: Vec<ResponsesToolDefinition>,
+    /// Vector stores the agent searches with `file_search`
+    pub vector_store_ids: Vec<String>,
+    /// Maximum number of tool-use turns per run. Capped by
+    /// `[features.server_tools] max_iterations`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_iterations: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request to create an agent
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CreateAgent {
+    /// Name of the agent (unique per organization)
+    #[validate(length(min = 1, max = 255))]
+    pub name: String,
+    #[validate(length(max = 1000))]
+    pub description: Option<String>,
+    /// Model the agent runs on, e.g. `openai/gpt-4o`
+    #[validate(length(min = 1, max = 255))]
+    pub model: String,
+    /// System prompt
+    pub instructions: Option<String>,
+    /// Server-executed tools: `file_search`, `web_search`, `shell`, or
+    /// function tools named after a configured HTTP tool
+    #[serde(default)]
+    pub tools: Vec<ResponsesToolDefinition>,
+    /// Vector stores to search with `file_search`
+    #[serde(default)]
+    #[validate(length(max = 16))]
+    pub vector_store_ids: Vec<String>,
+    /// Maximum number of tool-use turns per run
+    #[validate(range(min = 1, max = 100))]
+    pub max_iterations: Option<i32>,
+}
+
+/// Request to update an agent. Omitted fields are left unchanged.
+#[derive(Debug, Clone, Default, Deserialize, Validate)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct UpdateAgent {
+    #[validate(length(min = 1, max = 255))]
+    pub name: Option<String>,
+    #[validate(length(max = 1000))]
+    pub description: Option<String>,
+    #[validate(length(min = 1, max = 255))]
+    pub model: Option<String>,
+    pub instructions: Option<String>,
+    pub tools: Option<Vec<ResponsesToolDefinition>>,
+    #[validate(length(max = 16))]
+    pub vector_store_ids: Option<Vec<String>>,
+    #[validate(range(min = 1, max = 100))]
+    pub max_iterations: Option<i32>,
+}
+
+/// Status of an agent run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum AgentRunStatus {
+    InProgress,
+    /// The model produced its final answer
+    Completed,
+    /// The run stopped early, e.g. on the output token limit
+    Incomplete,
+    Failed,
+}
+
+impl AgentRunStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AgentRunStatus::InProgress => "in_progress",
+            AgentRunStatus::Completed => "completed",
+            AgentRunStatus::Incomplete => "incomplete",
+            AgentRunStatus::Failed => "failed",
+        }
+    }
+}
+
+impl std::str::FromStr for AgentRunStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "in_progress" => Ok(AgentRunStatus::InProgress),
+            "completed" => Ok(AgentRunStatus::Completed),
+            "incomplete" => Ok(AgentRunStatus::Incomplete),
+            "failed" => Ok(AgentRunStatus::Failed),
+            _ => Err(format!("Invalid agent run status: {}", s)),
+        }
+    }
+}
+
+/// One run of an agent, with the caller it is attributed to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct AgentRun {
+    pub id: Uuid,
+    pub agent_id: Uuid,
+    pub org_id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_account_id: Option<Uuid>,
+    /// Provider and model the run was routed to
+    pub provider: String,
+    pub model: String,
+    pub status: AgentRunStatus,
+    /// Input the run was started with
+    #[cfg_attr(feature = "utoipa", schema(value_type = Object))]
+    pub input: serde_json::Value,
+    /// Text of the agent's final answer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Tokens used across all of the run's turns
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    /// Cost of the run's tokens in microcents, when the model is priced
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost_microcents: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// Record of a newly started run.
+#[derive(Debug, Clone)]
+pub struct CreateAgentRun {
+    pub agent_id: Uuid,
+    pub org_id: Uuid,
+    pub project_id: Option<Uuid>,
+    pub user_id: Option<Uuid>,
+    pub api_key_id: Option<Uuid>,
+    pub service_account_id: Option<Uuid>,
+    pub provider: String,
+    pub model: String,
+    pub input: serde_json::Value,
+}
+
+/// Outcome of a finished run.
+#[derive(Debug, Clone)]
+pub struct AgentRunOutcome {
+    pub status: AgentRunStatus,
+    pub output_text: Option<String>,
+    pub error: Option<String>,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cost_microcents: Option<i64>,
+}
+
+/// One output item a run produced, in the order the run produced them: a
+/// message, a tool call, or a tool call's output.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct AgentRunStep {
+    pub id: Uuid,
+    pub run_id: Uuid,
+    /// Position of the step within the run, starting at 0
+    pub sequence: i32,
+    /// Output item type, e.g. `message`, `file_search_call` or `shell_call`
+    pub step_type: String,
+    /// The Responses API output item
+    #[cfg_attr(feature = "utoipa", schema(value_type = Object))]
+    pub content: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Request to run an agent
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CreateAgentRunRequest {
+    /// Text or input items, as accepted by the Responses API
+    #[cfg_attr(feature = "utoipa", schema(value_type = Object))]
+    pub input: serde_json::Value,
+    /// Stream the run's events as server-sent events instead of waiting for
+    /// it to finish
+    #[serde(default)]
+    pub stream: bool,
+}
+
+/// A run with its steps
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct AgentRunWithSteps {
+    #[serde(flatten)]
+    pub run: AgentRun,
+    pub steps: Vec<AgentRunStep>,
+}
+
+/// List of an agent's runs, newest first
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct AgentRunList {
+    pub data: Vec<AgentRun>,
+}
@@ -1,4 +1,4 @@
-use std::{fmt, net::IpAddr, str::FromStr};
+use std::{collections::BTreeMap, fmt, net::IpAddr, str::FromStr};
 
 use chrono::{DateTime, Utc};
 use ipnet::IpNet;
@@ -17,7 +17,8 @@ use crate::config::sovereignty::SovereigntyRequirements;
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum ApiKeyScope {
-    /// Access to chat completions and responses endpoints (`/v1/chat/completions`, `/v1/responses`)
+    /// Access to chat completions and responses endpoints (`/v1/chat/completions`, `/v1/responses`,
+    /// `/v1/agents/*`, `/v1/tokenize`, `/v1/estimate`)
     Chat,
     /// Access to legacy completions endpoint (`/v1/completions`)
     Completions,
@@ -27,7 +28,8 @@ pub enum ApiKeyScope {
     Images,
     /// Access to audio endpoints (`/v1/audio/*`)
     Audio,
-    /// Access to files and vector store endpoints (`/v1/files/*`, `/v1/vector_stores/*`)
+    /// Access to files, vector store and fine-tuning endpoints (`/v1/files/*`,
+    /// `/v1/vector_stores/*`, `/v1/fine_tuning/*`)
     Files,
     /// Access to models listing endpoint (`/v1/models`)
     Models,
@@ -98,13 +100,165 @@ impl FromStr for ApiKeyScope {
     }
 }
 
+/// Access level a request needs on a scope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScopeAccess {
+    /// Safe methods (`GET`, `HEAD`, `OPTIONS`)
+    Read,
+    /// Everything else
+    Write,
+}
+
+impl ScopeAccess {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScopeAccess::Read => "read",
+            ScopeAccess::Write => "write",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "read" => Some(ScopeAccess::Read),
+            "write" => Some(ScopeAccess::Write),
+            _ => None,
+        }
+    }
+}
+
+/// Admin API areas that can be granted individually (`admin:<area>[:<access>]`).
+pub const ADMIN_SCOPE_AREAS: &[&str] = &[
+    "access-reviews",
+    "agents",
+    "api-keys",
+    "audit-logs",
+    "conversations",
+    "dlq",
+    "dynamic-providers",
+    "elevations",
+    "email-log",
+    "entitlements",
+    "federation",
+    "http-tools",
+    "invitations",
+    "labels",
+    "me",
+    "members",
+    "model-access",
+    "model-catalog",
+    "model-degradation",
+    "model-pricing",
+    "network-policy",
+    "observability",
+    "organizations",
+    "parameter-governance",
+    "projects",
+    "provenance",
+    "providers",
+    "rbac-policies",
+    "reconciliation",
+    "report-runs",
+    "request-policies",
+    "responses",
+    "scheduled-tasks",
+    "scim-config",
+    "semantic-cache",
+    "service-accounts",
+    "sso-config",
+    "sso-connections",
+    "sso-group-mappings",
+    "teams",
+    "templates",
+    "usage",
+    "users",
+];
+
+/// The scope a single request needs.
+///
+/// Granted scope strings are matched from coarse to fine:
+/// - `chat` / `admin` — any access to the resource (all admin areas)
+/// - `chat:write`, `files:read`, `admin:read` — one access level
+/// - `admin:usage` — any access to one admin area
+/// - `admin:usage:read` — one access level on one admin area
+///
+/// `write` does not imply `read`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequiredScope {
+    pub scope: ApiKeyScope,
+    pub access: ScopeAccess,
+    /// Admin area for `/admin` requests; `None` when the path isn't in
+    /// [`ADMIN_SCOPE_AREAS`], in which case only area-wide admin scopes match
+    pub area: Option<&'static str>,
+}
+
+impl RequiredScope {
+    pub fn new(scope: ApiKeyScope, access: ScopeAccess) -> Self {
+        Self {
+            scope,
+            access,
+            area: None,
+        }
+    }
+
+    pub fn with_area(mut self, area: Option<&'static str>) -> Self {
+        self.area = area;
+        self
+    }
+
+    /// Whether a granted scope string satisfies this requirement.
+    pub fn is_granted_by(&self, granted: &str) -> bool {
+        let mut parts = granted.split(':');
+        if parts.next() != Some(self.scope.as_str()) {
+            return false;
+        }
+        let access_matches = |s: &str| ScopeAccess::parse(s) == Some(self.access);
+        match (parts.next(), parts.next(), parts.next()) {
+            (None, ..) => true,
+            (Some(access), None, _) if ScopeAccess::parse(access).is_some() => {
+                access_matches(access)
+            }
+            (Some(area), None, _) => self.area == Some(area),
+            (Some(area), Some(access), None) => self.area == Some(area) && access_matches(access),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for RequiredScope {
+    /// The most specific scope that grants this requirement.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.area {
+            Some(area) => write!(f, "{}:{}:{}", self.scope, area, self.access.as_str()),
+            None => write!(f, "{}:{}", self.scope, self.access.as_str()),
+        }
+    }
+}
+
+/// Check that a scope string is well-formed: `<scope>`, `<scope>:<access>`,
+/// or for admin, `admin:<area>` and `admin:<area>:<access>`.
+fn is_valid_scope(s: &str) -> bool {
+    let mut parts = s.split(':');
+    let Some(Ok(scope)) = parts.next().map(ApiKeyScope::from_str) else {
+        return false;
+    };
+    let is_area = |a: &str| scope == ApiKeyScope::Admin && ADMIN_SCOPE_AREAS.contains(&a);
+    match (parts.next(), parts.next(), parts.next()) {
+        (None, ..) => true,
+        (Some(second), None, _) => ScopeAccess::parse(second).is_some() || is_area(second),
+        (Some(area), Some(access), None) => is_area(area) && ScopeAccess::parse(access).is_some(),
+        _ => false,
+    }
+}
+
 /// Validate a list of scope strings.
 ///
-/// Returns `Ok(())` if all scopes are valid, or `Err` with a list of invalid scopes.
+/// Accepts coarse scopes (`chat`) and fine-grained ones (`chat:write`,
+/// `admin:usage:read`). Returns `Ok(())` if all scopes are valid, or `Err`
+/// with a list of invalid scopes.
 pub fn validate_scopes(scopes: &[String]) -> Result<(), Vec<String>> {
     let invalid: Vec<String> = scopes
         .iter()
-        .filter(|s| ApiKeyScope::from_str(s).is_err())
+        .filter(|s| !is_valid_scope(s))
         .cloned()
         .collect();
 
@@ -131,7 +285,9 @@ pub struct ApiKey {
     pub revoked_at: Option<DateTime<Utc>>,
     pub last_used_at: Option<DateTime<Utc>>,
     /// Permission scopes (null = full access)
-    /// Valid scopes: chat, completions, embeddings, images, audio, files, models, admin
+    /// Coarse scopes: chat, completions, embeddings, images, audio, files, models, admin.
+    /// Fine-grained: `<scope>:read`/`<scope>:write` (e.g. `chat:write`, `files:read`),
+    /// and per admin area `admin:<area>[:read|:write]` (e.g. `admin:usage:read`).
     pub scopes: Option<Vec<String>>,
     /// Allowed models (null = all models, supports wildcards like "gpt-4*")
     pub allowed_models: Option<Vec<String>>,
@@ -147,6 +303,10 @@ pub struct ApiKey {
     pub rotation_grace_until: Option<DateTime<Utc>>,
     /// Sovereignty requirements that restrict which models this key can access
     pub sovereignty_requirements: Option<SovereigntyRequirements>,
+    /// Free-form labels, e.g. `{"env": "prod"}`. Managed via
+    /// `/admin/v1/api-keys/{key_id}/labels`.
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
 }
 
 impl ApiKey {
@@ -154,11 +314,11 @@ impl ApiKey {
     ///
     /// Returns `true` if:
     /// - `scopes` is `None` (full access)
-    /// - `scopes` contains the required scope
-    pub fn has_scope(&self, required: ApiKeyScope) -> bool {
+    /// - any granted scope covers the requirement (see [`RequiredScope`])
+    pub fn has_scope(&self, required: &RequiredScope) -> bool {
         match &self.scopes {
             None => true, // No scopes = full access
-            Some(scopes) => scopes.iter().any(|s| s == required.as_str()),
+            Some(scopes) => scopes.iter().any(|s| required.is_granted_by(s)),
         }
     }
 
@@ -198,7 +358,7 @@ impl ApiKey {
 /// Supports exact match and trailing wildcard:
 /// - `"gpt-4"` matches `"gpt-4"` exactly
 /// - `"gpt-4*"` matches `"gpt-4"`, `"gpt-4o"`, `"gpt-4-turbo"`
-fn model_matches_pattern(model: &str, pattern: &str) -> bool {
+pub fn model_matches_pattern(model: &str, pattern: &str) -> bool {
     if let Some(prefix) = pattern.strip_suffix('*') {
         model.starts_with(prefix)
     } else {
@@ -258,7 +418,7 @@ fn is_valid_model_pattern(pattern: &str) -> bool {
 /// Check if an IP address matches an allowlist entry.
 ///
 /// Supports both CIDR notation (e.g., "192.168.1.0/24") and single IPs (e.g., "10.0.0.1").
-fn ip_matches_entry(ip: IpAddr, entry: &str) -> bool {
+pub fn ip_matches_entry(ip: IpAddr, entry: &str) -> bool {
     // Try parsing as CIDR
     if let Ok(net) = entry.parse::<IpNet>() {
         return net.contains(&ip);
@@ -338,7 +498,8 @@ pub struct CreateApiKey {
     pub budget_limit_cents: Option<i64>,
     pub budget_period: Option<BudgetPeriod>,
     pub expires_at: Option<DateTime<Utc>>,
-    /// Permission scopes (null = full access)
+    /// Permission scopes (null = full access), e.g. `chat:write`, `files:read`,
+    /// `admin:usage:read`
     pub scopes: Option<Vec<String>>,
     /// Allowed models (null = all models)
     pub allowed_models: Option<Vec<String>>,
@@ -362,7 +523,8 @@ pub struct CreateSelfServiceApiKey {
     pub budget_limit_cents: Option<i64>,
     pub budget_period: Option<BudgetPeriod>,
     pub expires_at: Option<DateTime<Utc>>,
-    /// Permission scopes (null = full access)
+    /// Permission scopes (null = full access), e.g. `chat:write`, `files:read`,
+    /// `admin:usage:read`
     pub scopes: Option<Vec<String>>,
     /// Allowed models (null = all models)
     pub allowed_models: Option<Vec<String>>,
@@ -418,6 +580,10 @@ pub struct ApiKeyWithOwner {
     pub service_account_id: Option<Uuid>,
     /// Roles from the service account (pre-fetched for RBAC evaluation)
     pub service_account_roles: Option<Vec<String>>,
+    /// Scheme the key is stored under (`api_keys.hash_scheme`)
+    pub hash_scheme: String,
+    /// Argon2id PHC string, for keys stored under the `argon2` scheme
+    pub key_verifier: Option<String>,
 }
 
 #[cfg(test)]
@@ -514,6 +680,36 @@ mod tests {
         assert!(invalid.contains(&"bar".to_string()));
     }
 
+    #[test]
+    fn test_validate_scopes_fine_grained() {
+        let valid: Vec<String> = [
+            "chat:write",
+            "files:read",
+            "admin:read",
+            "admin:usage",
+            "admin:usage:read",
+            "admin:service-accounts:write",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        assert!(validate_scopes(&valid).is_ok());
+
+        let invalid: Vec<String> = [
+            "chat:delete",
+            "chat:usage",
+            "chat:usage:read",
+            "admin:bogus",
+            "admin:usage:delete",
+            "admin:usage:read:extra",
+            "admin:",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        assert_eq!(validate_scopes(&invalid).unwrap_err(), invalid);
+    }
+
     fn make_test_api_key(scopes: Option<Vec<String>>) -> ApiKey {
         ApiKey {
             id: Uuid::new_v4(),
@@ -536,31 +732,91 @@ mod tests {
             rotated_from_key_id: None,
             rotation_grace_until: None,
             sovereignty_requirements: None,
+            labels: Default::default(),
         }
     }
 
+    fn write(scope: ApiKeyScope) -> RequiredScope {
+        RequiredScope::new(scope, ScopeAccess::Write)
+    }
+
     #[test]
     fn test_has_scope_none_means_full_access() {
         let key = make_test_api_key(None);
-        assert!(key.has_scope(ApiKeyScope::Chat));
-        assert!(key.has_scope(ApiKeyScope::Embeddings));
-        assert!(key.has_scope(ApiKeyScope::Admin));
+        assert!(key.has_scope(&write(ApiKeyScope::Chat)));
+        assert!(key.has_scope(&write(ApiKeyScope::Embeddings)));
+        assert!(key.has_scope(&write(ApiKeyScope::Admin)));
     }
 
     #[test]
     fn test_has_scope_with_specific_scopes() {
         let key = make_test_api_key(Some(vec!["chat".to_string(), "embeddings".to_string()]));
-        assert!(key.has_scope(ApiKeyScope::Chat));
-        assert!(key.has_scope(ApiKeyScope::Embeddings));
-        assert!(!key.has_scope(ApiKeyScope::Admin));
-        assert!(!key.has_scope(ApiKeyScope::Images));
+        assert!(key.has_scope(&write(ApiKeyScope::Chat)));
+        assert!(key.has_scope(&write(ApiKeyScope::Embeddings)));
+        assert!(!key.has_scope(&write(ApiKeyScope::Admin)));
+        assert!(!key.has_scope(&write(ApiKeyScope::Images)));
     }
 
     #[test]
     fn test_has_scope_empty_scopes() {
         let key = make_test_api_key(Some(vec![]));
-        assert!(!key.has_scope(ApiKeyScope::Chat));
-        assert!(!key.has_scope(ApiKeyScope::Admin));
+        assert!(!key.has_scope(&write(ApiKeyScope::Chat)));
+        assert!(!key.has_scope(&write(ApiKeyScope::Admin)));
+    }
+
+    #[test]
+    fn test_has_scope_access_levels() {
+        let key = make_test_api_key(Some(vec![
+            "files:read".to_string(),
+            "chat:write".to_string(),
+        ]));
+        assert!(key.has_scope(&RequiredScope::new(ApiKeyScope::Files, ScopeAccess::Read)));
+        assert!(!key.has_scope(&write(ApiKeyScope::Files)));
+        assert!(key.has_scope(&write(ApiKeyScope::Chat)));
+        // write does not imply read
+        assert!(!key.has_scope(&RequiredScope::new(ApiKeyScope::Chat, ScopeAccess::Read)));
+    }
+
+    #[test]
+    fn test_has_scope_admin_areas() {
+        let usage_read =
+            RequiredScope::new(ApiKeyScope::Admin, ScopeAccess::Read).with_area(Some("usage"));
+        let usage_write =
+            RequiredScope::new(ApiKeyScope::Admin, ScopeAccess::Write).with_area(Some("usage"));
+        let users_read =
+            RequiredScope::new(ApiKeyScope::Admin, ScopeAccess::Read).with_area(Some("users"));
+
+        let key = make_test_api_key(Some(vec!["admin:usage:read".to_string()]));
+        assert!(key.has_scope(&usage_read));
+        assert!(!key.has_scope(&usage_write));
+        assert!(!key.has_scope(&users_read));
+
+        let key = make_test_api_key(Some(vec!["admin:usage".to_string()]));
+        assert!(key.has_scope(&usage_read));
+        assert!(key.has_scope(&usage_write));
+        assert!(!key.has_scope(&users_read));
+
+        let key = make_test_api_key(Some(vec!["admin:read".to_string()]));
+        assert!(key.has_scope(&usage_read));
+        assert!(key.has_scope(&users_read));
+        assert!(!key.has_scope(&usage_write));
+
+        // Unknown admin paths only match area-wide scopes
+        let unknown = RequiredScope::new(ApiKeyScope::Admin, ScopeAccess::Read);
+        assert!(key.has_scope(&unknown));
+        let key = make_test_api_key(Some(vec!["admin:usage:read".to_string()]));
+        assert!(!key.has_scope(&unknown));
+    }
+
+    #[test]
+    fn test_required_scope_display() {
+        assert_eq!(write(ApiKeyScope::Chat).to_string(), "chat:write");
+        assert_eq!(
+            RequiredScope::new(ApiKeyScope::Admin, ScopeAccess::Read)
+                .with_area(Some("usage"))
+                .to_string(),
+            "admin:usage:read"
+        );
     }
 
     // Helper function to create test API key with allowed_models
@@ -586,6 +842,7 @@ mod tests {
             rotated_from_key_id: None,
             rotation_grace_until: None,
             sovereignty_requirements: None,
+            labels: Default::default(),
         }
     }
 
@@ -778,6 +1035,7 @@ mod tests {
             rotated_from_key_id: None,
             rotation_grace_until: None,
             sovereignty_requirements: None,
+            labels: Default::default(),
         }
     }
 
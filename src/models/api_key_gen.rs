@@ -1,17 +1,149 @@
+use std::sync::Arc;
+
+use argon2::{Argon2, PasswordHash, PasswordHasher, PasswordVerifier, password_hash::SaltString};
 use base64::Engine;
+use hmac::{Hmac, Mac};
 use rand::Rng;
 use sha2::{Digest, Sha256};
 use subtle::ConstantTimeEq;
 
+use crate::config::{ApiKeyAuthConfig, HashAlgorithm};
+
+type HmacSha256 = Hmac<Sha256>;
+
 /// Default API key prefix
 pub const DEFAULT_API_KEY_PREFIX: &str = "gw_live_";
 
+/// How an API key is stored: the digest used to look it up, the scheme that
+/// produced it, and for Argon2 the salted hash checked after lookup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiKeyHash {
+    /// Deterministic digest stored in `api_keys.key_hash`
+    pub key_hash: String,
+    pub scheme: HashAlgorithm,
+    /// Argon2id PHC string, for [`HashAlgorithm::Argon2`] only
+    pub verifier: Option<String>,
+}
+
+impl ApiKeyHash {
+    /// A legacy unpeppered SHA-256 hash.
+    pub fn sha256(key_hash: impl Into<String>) -> Self {
+        Self {
+            key_hash: key_hash.into(),
+            scheme: HashAlgorithm::Sha256,
+            verifier: None,
+        }
+    }
+}
+
+/// Hashes and verifies API keys according to `[auth.api_key]`.
+///
+/// Every scheme stores a deterministic digest so keys can be found by index:
+/// plain SHA-256, or HMAC-SHA256 keyed with the pepper. Argon2 adds a salted
+/// hash on top, verified only when a lookup misses the cache.
+#[derive(Clone)]
+pub struct ApiKeyHasher {
+    algorithm: HashAlgorithm,
+    pepper: Option<Arc<[u8]>>,
+    legacy_fallback: bool,
+}
+
+impl Default for ApiKeyHasher {
+    fn default() -> Self {
+        Self::from_config(&ApiKeyAuthConfig::default())
+    }
+}
+
+impl ApiKeyHasher {
+    pub fn from_config(config: &ApiKeyAuthConfig) -> Self {
+        Self {
+            algorithm: config.hash_algorithm,
+            pepper: config.pepper.as_deref().map(|p| Arc::from(p.as_bytes())),
+            legacy_fallback: config.legacy_fallback,
+        }
+    }
+
+    /// The scheme new and re-hashed keys are stored under.
+    pub fn algorithm(&self) -> HashAlgorithm {
+        self.algorithm
+    }
+
+    /// Digest under which a key hashed with the configured scheme is stored.
+    /// Also used as the key's cache key.
+    pub fn lookup_hash(&self, raw_key: &str) -> String {
+        match (&self.pepper, self.algorithm) {
+            (_, HashAlgorithm::Sha256) | (None, _) => hash_api_key(raw_key),
+            (Some(pepper), _) => {
+                let mut mac =
+                    HmacSha256::new_from_slice(pepper).expect("HMAC accepts keys of any length");
+                mac.update(raw_key.as_bytes());
+                hex::encode(mac.finalize().into_bytes())
+            }
+        }
+    }
+
+    /// Digest of the legacy SHA-256 scheme, if it differs from
+    /// [`lookup_hash`](Self::lookup_hash) and legacy keys are still accepted.
+    pub fn legacy_lookup_hash(&self, raw_key: &str) -> Option<String> {
+        let uses_pepper = self.pepper.is_some() && self.algorithm != HashAlgorithm::Sha256;
+        (self.legacy_fallback && uses_pepper).then(|| hash_api_key(raw_key))
+    }
+
+    /// Hash a key for storage under the configured scheme.
+    pub fn hash(&self, raw_key: &str) -> ApiKeyHash {
+        let verifier = (self.algorithm == HashAlgorithm::Argon2).then(|| argon2_hash(raw_key));
+        ApiKeyHash {
+            key_hash: self.lookup_hash(raw_key),
+            scheme: self.algorithm,
+            verifier,
+        }
+    }
+
+    /// Check a key found by digest against its stored scheme.
+    ///
+    /// The digest match is sufficient for SHA-256 and HMAC; Argon2 keys must
+    /// also match their salted hash. Unknown schemes are rejected.
+    pub fn verify(&self, raw_key: &str, scheme: &str, verifier: Option<&str>) -> bool {
+        match scheme.parse::<HashAlgorithm>() {
+            Ok(HashAlgorithm::Argon2) => verifier.is_some_and(|v| argon2_verify(raw_key, v)),
+            Ok(_) => true,
+            Err(_) => false,
+        }
+    }
+
+    /// Whether a key stored under `scheme` and `key_hash` should be re-hashed
+    /// to the configured scheme.
+    pub fn needs_rehash(&self, raw_key: &str, scheme: &str, key_hash: &str) -> bool {
+        scheme != self.algorithm.as_str() || key_hash != self.lookup_hash(raw_key)
+    }
+}
+
+/// Argon2id (default parameters) with a random salt, as a PHC string.
+fn argon2_hash(raw_key: &str) -> String {
+    let mut salt = [0u8; 16];
+    rand::thread_rng().fill(&mut salt);
+    let salt = SaltString::encode_b64(&salt).expect("16-byte salt is within PHC limits");
+    Argon2::default()
+        .hash_password(raw_key.as_bytes(), &salt)
+        .expect("default Argon2 parameters are valid")
+        .to_string()
+}
+
+/// Verify against a PHC string, using the parameters recorded in it.
+fn argon2_verify(raw_key: &str, phc: &str) -> bool {
+    PasswordHash::new(phc).is_ok_and(|hash| {
+        Argon2::default()
+            .verify_password(raw_key.as_bytes(), &hash)
+            .is_ok()
+    })
+}
+
 /// Generate a new API key with the given prefix.
 ///
 /// Returns a tuple of (raw_key, key_hash) where:
 /// - raw_key is the full key to show the user (only shown once on creation)
-/// - key_hash is the SHA-256 hash to store in the database
-pub fn generate_api_key_with_prefix(prefix: &str) -> (String, String) {
+/// - key_hash is how the key is stored, per `hasher`
+pub fn generate_api_key_with_prefix(prefix: &str, hasher: &ApiKeyHasher) -> (String, ApiKeyHash) {
     // Generate 32 random bytes (256 bits of entropy)
     let mut rng = rand::thread_rng();
     let mut random_bytes = [0u8; 32];
@@ -24,7 +156,7 @@ pub fn generate_api_key_with_prefix(prefix: &str) -> (String, String) {
     let raw_key = format!("{}{}", prefix, random_part);
 
     // Hash the key for storage
-    let key_hash = hash_api_key(&raw_key);
+    let key_hash = hasher.hash(&raw_key);
 
     (raw_key, key_hash)
 }
@@ -36,7 +168,9 @@ pub fn generate_api_key_with_prefix(prefix: &str) -> (String, String) {
 /// - key_hash is the SHA-256 hash to store in the database
 #[allow(dead_code)] // Used in tests; public API convenience wrapper
 pub fn generate_api_key() -> (String, String) {
-    generate_api_key_with_prefix(DEFAULT_API_KEY_PREFIX)
+    let (raw_key, hash) =
+        generate_api_key_with_prefix(DEFAULT_API_KEY_PREFIX, &ApiKeyHasher::default());
+    (raw_key, hash.key_hash)
 }
 
 /// Hash an API key using SHA-256
@@ -115,7 +249,7 @@ mod tests {
 
     #[test]
     fn test_generate_api_key_with_custom_prefix() {
-        let (raw_key, _hash) = generate_api_key_with_prefix("custom_");
+        let (raw_key, _hash) = generate_api_key_with_prefix("custom_", &ApiKeyHasher::default());
 
         assert!(raw_key.starts_with("custom_"));
         assert_eq!(raw_key.len(), 7 + 43); // "custom_" is 7 chars
@@ -171,4 +305,68 @@ mod tests {
         assert!(!verify_api_key(&key1, &hash2));
         assert!(!verify_api_key(&key2, &hash1));
     }
+
+    fn make_hasher(algorithm: HashAlgorithm, pepper: Option<&str>) -> ApiKeyHasher {
+        ApiKeyHasher::from_config(&ApiKeyAuthConfig {
+            hash_algorithm: algorithm,
+            pepper: pepper.map(String::from),
+            ..ApiKeyAuthConfig::default()
+        })
+    }
+
+    const PEPPER: &str = "0123456789abcdef0123456789abcdef";
+
+    #[test]
+    fn test_default_hasher_matches_legacy_sha256() {
+        let hasher = ApiKeyHasher::default();
+        let hash = hasher.hash("gw_live_test123");
+        assert_eq!(hash, ApiKeyHash::sha256(hash_api_key("gw_live_test123")));
+        assert!(hasher.legacy_lookup_hash("gw_live_test123").is_none());
+        assert!(!hasher.needs_rehash("gw_live_test123", "sha256", &hash.key_hash));
+    }
+
+    #[test]
+    fn test_hmac_hasher_uses_pepper() {
+        let hasher = make_hasher(HashAlgorithm::HmacSha256, Some(PEPPER));
+        let hash = hasher.hash("gw_live_test123");
+        assert_eq!(hash.scheme, HashAlgorithm::HmacSha256);
+        assert_eq!(hash.key_hash.len(), 64);
+        assert_ne!(hash.key_hash, hash_api_key("gw_live_test123"));
+        assert!(hash.verifier.is_none());
+
+        // A different pepper yields a different digest
+        let other = make_hasher(HashAlgorithm::HmacSha256, Some(&PEPPER.replace('0', 'x')));
+        assert_ne!(other.lookup_hash("gw_live_test123"), hash.key_hash);
+
+        // Legacy keys are found by their SHA-256 digest and flagged for re-hash
+        let legacy = hash_api_key("gw_live_test123");
+        assert_eq!(
+            hasher.legacy_lookup_hash("gw_live_test123"),
+            Some(legacy.clone())
+        );
+        assert!(hasher.needs_rehash("gw_live_test123", "sha256", &legacy));
+        assert!(!hasher.needs_rehash("gw_live_test123", "hmac_sha256", &hash.key_hash));
+    }
+
+    #[test]
+    fn test_argon2_hasher_verifies_salted_hash() {
+        let hasher = make_hasher(HashAlgorithm::Argon2, None);
+        let a = hasher.hash("gw_live_test123");
+        let b = hasher.hash("gw_live_test123");
+
+        // Lookup digest is deterministic, the verifier is salted
+        assert_eq!(a.key_hash, b.key_hash);
+        assert_ne!(a.verifier, b.verifier);
+        let verifier = a.verifier.as_deref().unwrap();
+        assert!(verifier.starts_with("$argon2id$"));
+
+        assert!(hasher.verify("gw_live_test123", "argon2", Some(verifier)));
+        assert!(!hasher.verify("gw_live_wrong", "argon2", Some(verifier)));
+        assert!(!hasher.verify("gw_live_test123", "argon2", None));
+        assert!(!hasher.verify("gw_live_test123", "rot13", None));
+        assert!(hasher.verify("gw_live_test123", "sha256", None));
+
+        // A SHA-256 key shares the digest but still needs a verifier
+        assert!(hasher.needs_rehash("gw_live_test123", "sha256", &a.key_hash));
+    }
 }
@@ -0,0 +1,303 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    BudgetPeriod, ConversationSummarySettings, DbModelPricing, ModelAccessPolicy,
+    ModelDegradationPolicy, OrgNetworkPolicy, ParameterGovernance, RbacPolicyEffect,
+    RequestDefaults,
+};
+use crate::config::DataResidencyPolicy;
+
+/// Bundle format version written by this gateway.
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Organizational structure exported by `/admin/v1/export` and restored by
+/// `/admin/v1/import`.
+///
+/// Entities are referenced by slug, name or external ID rather than by
+/// database ID, so a bundle can be imported into another environment.
+/// Bundles never contain secrets.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct Bundle {
+    /// Bundle format version
+    pub version: u32,
+    /// When the bundle was exported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exported_at: Option<DateTime<Utc>>,
+    /// Users referenced by memberships
+    #[serde(default)]
+    pub users: Vec<BundleUser>,
+    #[serde(default)]
+    pub organizations: Vec<BundleOrganization>,
+    /// Global model pricing
+    #[serde(default)]
+    pub model_pricing: Vec<BundleModelPricing>,
+}
+
+/// A user, identified by external ID.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct BundleUser {
+    pub external_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// A user's membership of an organization, team or project.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct BundleMember {
+    /// External ID of the user
+    pub user: String,
+    pub role: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct BundleOrganization {
+    pub slug: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_residency: Option<DataResidencyPolicy>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_defaults: Option<RequestDefaults>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conversation_summaries: Option<ConversationSummarySettings>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network_policy: Option<OrgNetworkPolicy>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parameter_governance: Option<ParameterGovernance>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_access: Option<ModelAccessPolicy>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_degradation: Option<ModelDegradationPolicy>,
+    #[serde(default)]
+    pub members: Vec<BundleMember>,
+    #[serde(default)]
+    pub teams: Vec<BundleTeam>,
+    #[serde(default)]
+    pub projects: Vec<BundleProject>,
+    #[serde(default)]
+    pub rbac_policies: Vec<BundleRbacPolicy>,
+    /// API key metadata. Keys can't be restored without their secrets, so
+    /// these are reported but not imported.
+    #[serde(default)]
+    pub api_keys: Vec<BundleApiKey>,
+    /// Organization and project model pricing
+    #[serde(default)]
+    pub model_pricing: Vec<BundleModelPricing>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct BundleTeam {
+    pub slug: String,
+    pub name: String,
+    #[serde(default)]
+    pub members: Vec<BundleMember>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct BundleProject {
+    pub slug: String,
+    pub name: String,
+    /// Slug of the team the project belongs to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub team: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_defaults: Option<RequestDefaults>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parameter_governance: Option<ParameterGovernance>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_access: Option<ModelAccessPolicy>,
+    #[serde(default)]
+    pub members: Vec<BundleMember>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct BundleRbacPolicy {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub resource: String,
+    pub action: String,
+    pub condition: String,
+    pub effect: RbacPolicyEffect,
+    #[serde(default)]
+    pub priority: i32,
+    pub enabled: bool,
+}
+
+/// Metadata of an active API key. The key itself and its hash are never
+/// exported.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct BundleApiKey {
+    pub name: String,
+    pub key_prefix: String,
+    pub owner: BundleApiKeyOwner,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scopes: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_models: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ip_allowlist: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget_limit_cents: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget_period: Option<BudgetPeriod>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit_rpm: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit_tpm: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Owner of an exported API key within its organization.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BundleApiKeyOwner {
+    Organization,
+    Team { team: String },
+    Project { project: String },
+}
+
+/// Model pricing. Inside an organization, pricing applies to the whole
+/// organization unless `project` is set.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct BundleModelPricing {
+    /// Slug of the project the pricing applies to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    pub provider: String,
+    pub model: String,
+    #[serde(default)]
+    pub input_per_1m_tokens: i64,
+    #[serde(default)]
+    pub output_per_1m_tokens: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub per_image: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub per_request: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cached_input_per_1m_tokens: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_write_per_1m_tokens: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning_per_1m_tokens: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub per_second: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub per_1m_characters: Option<i64>,
+}
+
+impl BundleModelPricing {
+    pub fn from_db(pricing: &DbModelPricing, project: Option<String>) -> Self {
+        Self {
+            project,
+            provider: pricing.provider.clone(),
+            model: pricing.model.clone(),
+            input_per_1m_tokens: pricing.input_per_1m_tokens,
+            output_per_1m_tokens: pricing.output_per_1m_tokens,
+            per_image: pricing.per_image,
+            per_request: pricing.per_request,
+            cached_input_per_1m_tokens: pricing.cached_input_per_1m_tokens,
+            cache_write_per_1m_tokens: pricing.cache_write_per_1m_tokens,
+            reasoning_per_1m_tokens: pricing.reasoning_per_1m_tokens,
+            per_second: pricing.per_second,
+            per_1m_characters: pricing.per_1m_characters,
+        }
+    }
+}
+
+/// What importing a bundle does to one entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ImportAction {
+    Create,
+    Update,
+    Unchanged,
+    /// Not imported; see the change's `note`
+    Skip,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ImportChange {
+    /// Kind of entity, e.g. `organization` or `team_member`
+    pub resource: String,
+    /// Natural key of the entity, e.g. `acme/platform`
+    pub key: String,
+    pub action: ImportAction,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// Result of importing a bundle, or of validating it with `dry_run`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ImportReport {
+    /// Whether the changes were only planned, not applied
+    pub dry_run: bool,
+    pub changes: Vec<ImportChange>,
+}
+
+impl ImportReport {
+    /// Number of changes with the given action.
+    pub fn count(&self, action: ImportAction) -> usize {
+        self.changes.iter().filter(|c| c.action == action).count()
+    }
+}
+
+/// Serialization format of a bundle.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum BundleFormat {
+    #[default]
+    Json,
+    Yaml,
+}
+
+/// Query parameters for the export endpoint
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::IntoParams, utoipa::ToSchema))]
+#[cfg_attr(feature = "utoipa", into_params(parameter_in = Query))]
+pub struct ExportBundleQuery {
+    /// Bundle format (json or yaml)
+    #[cfg_attr(feature = "utoipa", param(default = "json"))]
+    #[serde(default)]
+    pub format: BundleFormat,
+    /// Comma-separated organization slugs to export (default: all)
+    #[cfg_attr(feature = "utoipa", param(nullable))]
+    pub orgs: Option<String>,
+}
+
+/// Query parameters for the import endpoint
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::IntoParams, utoipa::ToSchema))]
+#[cfg_attr(feature = "utoipa", into_params(parameter_in = Query))]
+pub struct ImportBundleQuery {
+    /// Validate the bundle and report changes without applying them
+    #[cfg_attr(feature = "utoipa", param(default = false))]
+    #[serde(default)]
+    pub dry_run: bool,
+}
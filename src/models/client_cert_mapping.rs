@@ -0,0 +1,143 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Which part of a client certificate a mapping matches against.
+///
+/// When a certificate matches several mappings, the most specific wins in
+/// the order the variants are declared: fingerprint, then URI, DNS and email
+/// SANs, then the subject common name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum CertMatchType {
+    /// SHA-256 fingerprint of the DER-encoded certificate (hex, colons optional).
+    FingerprintSha256,
+    /// A `uniformResourceIdentifier` SAN, e.g. a SPIFFE ID.
+    SanUri,
+    /// A `dNSName` SAN.
+    SanDns,
+    /// An `rfc822Name` (email) SAN.
+    SanEmail,
+    /// The subject's common name (CN).
+    SubjectCn,
+}
+
+impl CertMatchType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::FingerprintSha256 => "fingerprint_sha256",
+            Self::SanUri => "san_uri",
+            Self::SanDns => "san_dns",
+            Self::SanEmail => "san_email",
+            Self::SubjectCn => "subject_cn",
+        }
+    }
+
+    /// Canonical form used for storage and lookup. Fingerprints are
+    /// lowercased with separators stripped; DNS names and email addresses are
+    /// case-insensitive and lowercased. URIs and CNs are compared verbatim.
+    pub fn normalize(&self, value: &str) -> String {
+        let value = value.trim();
+        match self {
+            Self::FingerprintSha256 => value
+                .chars()
+                .filter(|c| *c != ':')
+                .collect::<String>()
+                .to_ascii_lowercase(),
+            Self::SanDns | Self::SanEmail => value.to_ascii_lowercase(),
+            Self::SanUri | Self::SubjectCn => value.to_string(),
+        }
+    }
+}
+
+impl std::str::FromStr for CertMatchType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fingerprint_sha256" => Ok(Self::FingerprintSha256),
+            "san_uri" => Ok(Self::SanUri),
+            "san_dns" => Ok(Self::SanDns),
+            "san_email" => Ok(Self::SanEmail),
+            "subject_cn" => Ok(Self::SubjectCn),
+            _ => Err(format!("Invalid certificate match type: {}", s)),
+        }
+    }
+}
+
+/// Maps client certificates to a service account for mTLS authentication.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ClientCertMapping {
+    /// Unique identifier
+    pub id: Uuid,
+    /// Organization the service account belongs to
+    pub org_id: Uuid,
+    /// Service account that matching certificates authenticate as
+    pub service_account_id: Uuid,
+    /// Certificate field to match
+    pub match_type: CertMatchType,
+    /// Value to match (normalized)
+    pub match_value: String,
+    /// Optional description
+    pub description: Option<String>,
+    /// When the mapping was created
+    pub created_at: DateTime<Utc>,
+}
+
+/// Request to map a client certificate to a service account
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CreateClientCertMapping {
+    /// Certificate field to match
+    pub match_type: CertMatchType,
+    /// Value to match, e.g. `spiffe://example.org/billing` for `san_uri`
+    #[validate(length(min = 1, max = 1024))]
+    pub match_value: String,
+    /// Optional description
+    #[validate(length(max = 1000))]
+    pub description: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            CertMatchType::FingerprintSha256.normalize(" AB:cd:01 "),
+            "abcd01"
+        );
+        assert_eq!(
+            CertMatchType::SanDns.normalize("Billing.Example.COM"),
+            "billing.example.com"
+        );
+        assert_eq!(
+            CertMatchType::SanUri.normalize("spiffe://Example.org/Billing"),
+            "spiffe://Example.org/Billing"
+        );
+    }
+
+    #[test]
+    fn test_match_type_precedence() {
+        let mut types = vec![
+            CertMatchType::SubjectCn,
+            CertMatchType::SanDns,
+            CertMatchType::FingerprintSha256,
+            CertMatchType::SanUri,
+        ];
+        types.sort();
+        assert_eq!(
+            types,
+            vec![
+                CertMatchType::FingerprintSha256,
+                CertMatchType::SanUri,
+                CertMatchType::SanDns,
+                CertMatchType::SubjectCn,
+            ]
+        );
+    }
+}
@@ -9,6 +9,39 @@ use validator::Validate;
 pub struct Message {
     pub role: String,
     pub content: String,
+    /// Annotations on the message text, in the Responses API `annotations`
+    /// shape (e.g. `file_citation`s from file_search), so clients can render
+    /// source links when the conversation is reloaded.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "utoipa", schema(value_type = Vec<Object>))]
+    pub annotations: Vec<serde_json::Value>,
+    /// Uploaded files attached to the message. Only `file_id` is required when
+    /// writing; `filename` and `content_type` are filled in from the file.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<MessageAttachment>,
+}
+
+/// A Files API upload attached to a conversation message
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct MessageAttachment {
+    /// File ID (serialized with `file-` prefix)
+    #[serde(with = "super::prefixed_id::file_id_serde")]
+    #[cfg_attr(feature = "utoipa", schema(value_type = String, example = "file-550e8400-e29b-41d4-a716-446655440000"))]
+    pub file_id: Uuid,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filename: Option<String>,
+    /// MIME content type of the file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+}
+
+impl MessageAttachment {
+    pub fn is_image(&self) -> bool {
+        self.content_type
+            .as_deref()
+            .is_some_and(|ct| ct.starts_with("image/"))
+    }
 }
 
 /// Owner type for conversations
@@ -56,6 +89,10 @@ pub struct Conversation {
     /// Pin order for the conversation. NULL = not pinned, 0-N = pinned with order (lower = higher in list)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pin_order: Option<i32>,
+    /// Rolling summary generated in the background (see
+    /// `[features.conversation_summaries]`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -151,3 +188,80 @@ pub struct SetPinOrder {
     /// The pin order (0 = first, higher = lower in list). Set to null to unpin.
     pub pin_order: Option<i32>,
 }
+
+/// An alternate message history within a conversation.
+///
+/// Branches are created by editing or deleting a message: the new branch
+/// inherits the first `fork_index` messages of its parent and diverges from
+/// there. Exactly one branch is active at a time, and the conversation's
+/// `messages` always mirror it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ConversationBranch {
+    pub id: Uuid,
+    pub conversation_id: Uuid,
+    /// Branch this one was forked from. `None` for the root branch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_branch_id: Option<Uuid>,
+    /// Number of messages inherited from the parent branch
+    pub fork_index: i32,
+    pub messages: Vec<Message>,
+    /// Whether this branch is the conversation's current history
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request to fork a conversation at a message
+///
+/// With `message` set, the message at `message_index` is replaced (edit);
+/// without it, the message and everything after it are dropped (delete).
+/// Either way the new branch becomes active.
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ForkConversation {
+    /// Index of the message to edit or delete
+    pub message_index: u32,
+    /// Replacement message. Omit to delete the message instead.
+    #[serde(default)]
+    pub message: Option<Message>,
+}
+
+/// Request to switch a conversation to another branch
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct SwitchConversationBranch {
+    /// Branch to make active
+    pub branch_id: Uuid,
+}
+
+/// Organization settings for background conversation title and summary generation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ConversationSummarySettings {
+    /// Generate titles and summaries for the organization's project conversations
+    #[serde(default = "default_summaries_enabled")]
+    pub enabled: bool,
+    /// Model to use instead of the gateway default. Served by the provider
+    /// configured in `[features.conversation_summaries]`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(length(min = 1, max = 255))]
+    pub model: Option<String>,
+}
+
+fn default_summaries_enabled() -> bool {
+    true
+}
+
+/// Output of the background summarizer, written back onto a conversation
+#[derive(Debug, Clone)]
+pub struct SetConversationSummary {
+    pub summary: String,
+    /// Number of messages the summary covers
+    pub message_count: i32,
+    /// Generated title. Only applied while the conversation still has
+    /// `expected_title`, so a rename made during generation is kept.
+    pub title: Option<String>,
+    /// Title the conversation had when the summary was generated
+    pub expected_title: String,
+}
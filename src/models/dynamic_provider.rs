@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -23,6 +25,10 @@ pub struct DynamicProvider {
     /// Sovereignty and compliance metadata
     pub sovereignty: Option<SovereigntyMetadata>,
     pub is_enabled: bool,
+    /// Free-form labels, e.g. `{"env": "prod"}`. Managed via
+    /// `/admin/v1/dynamic-providers/{id}/labels`.
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -44,6 +50,10 @@ pub struct DynamicProviderResponse {
     /// Sovereignty and compliance metadata
     pub sovereignty: Option<SovereigntyMetadata>,
     pub is_enabled: bool,
+    /// Free-form labels, e.g. `{"env": "prod"}`. Managed via
+    /// `/admin/v1/dynamic-providers/{id}/labels`.
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -95,6 +105,7 @@ impl From<DynamicProvider> for DynamicProviderResponse {
             models: p.models,
             sovereignty: p.sovereignty,
             is_enabled: p.is_enabled,
+            labels: p.labels,
             created_at: p.created_at,
             updated_at: p.updated_at,
         }
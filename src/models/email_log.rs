@@ -0,0 +1,126 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// What a notification email is about. Each kind has its own template.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum EmailKind {
+    /// Invite link for an organization or team invitation.
+    Invitation,
+    /// Budget warning threshold reached.
+    SpendAlert,
+    /// Periodic reminder to review stale users and API keys.
+    AccessReviewReminder,
+    /// Dead-letter queue entry that exhausted its retries.
+    DlqFailure,
+}
+
+impl EmailKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Invitation => "invitation",
+            Self::SpendAlert => "spend_alert",
+            Self::AccessReviewReminder => "access_review_reminder",
+            Self::DlqFailure => "dlq_failure",
+        }
+    }
+}
+
+impl std::str::FromStr for EmailKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "invitation" => Ok(Self::Invitation),
+            "spend_alert" => Ok(Self::SpendAlert),
+            "access_review_reminder" => Ok(Self::AccessReviewReminder),
+            "dlq_failure" => Ok(Self::DlqFailure),
+            _ => Err(format!("Invalid email kind: {}", s)),
+        }
+    }
+}
+
+/// Outcome of a send attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum EmailSendStatus {
+    /// Accepted by the SMTP relay.
+    Sent,
+    /// Rejected by the relay or not built (e.g. an invalid address).
+    Failed,
+    /// Dropped by the gateway's send rate limit.
+    RateLimited,
+}
+
+impl EmailSendStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Sent => "sent",
+            Self::Failed => "failed",
+            Self::RateLimited => "rate_limited",
+        }
+    }
+}
+
+impl std::str::FromStr for EmailSendStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sent" => Ok(Self::Sent),
+            "failed" => Ok(Self::Failed),
+            "rate_limited" => Ok(Self::RateLimited),
+            _ => Err(format!("Invalid email send status: {}", s)),
+        }
+    }
+}
+
+/// One send attempt in the email send log. Bodies aren't stored, since they
+/// can carry invite links.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct EmailLogEntry {
+    /// Unique identifier
+    pub id: Uuid,
+    /// Organization the email was sent for, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<Uuid>,
+    /// What the email was about
+    pub kind: EmailKind,
+    /// Sender address used
+    pub from_address: String,
+    /// Recipient addresses
+    pub recipients: Vec<String>,
+    /// Rendered subject line
+    pub subject: String,
+    /// Outcome of the attempt
+    pub status: EmailSendStatus,
+    /// Error reported by the relay or while building the message
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// When the send was attempted
+    pub created_at: DateTime<Utc>,
+}
+
+/// Input for recording a send attempt.
+#[derive(Debug, Clone)]
+pub struct NewEmailLogEntry {
+    pub org_id: Option<Uuid>,
+    pub kind: EmailKind,
+    pub from_address: String,
+    pub recipients: Vec<String>,
+    pub subject: String,
+    pub status: EmailSendStatus,
+    pub error: Option<String>,
+}
+
+/// Filters for listing the send log.
+#[derive(Debug, Clone, Default)]
+pub struct EmailLogFilter {
+    pub org_id: Option<Uuid>,
+    pub kind: Option<EmailKind>,
+    pub status: Option<EmailSendStatus>,
+}
@@ -0,0 +1,254 @@
+use serde::{Deserialize, Serialize};
+
+use crate::config::EntitlementsConfig;
+
+/// A feature that depends on the organization's plan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntitledFeature {
+    SemanticCache,
+    FileSearch,
+    BatchApi,
+}
+
+impl EntitledFeature {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::SemanticCache => "semantic_cache",
+            Self::FileSearch => "file_search",
+            Self::BatchApi => "batch_api",
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::SemanticCache => "Semantic caching",
+            Self::FileSearch => "File search",
+            Self::BatchApi => "The batch API",
+        }
+    }
+}
+
+/// An organization's plan and per-feature overrides.
+///
+/// Managed via `/admin/v1/organizations/{slug}/entitlements`. Fields left
+/// unset come from the plan; without a plan the organization is on
+/// `[features.entitlements] default_plan`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct OrgEntitlements {
+    /// Plan the organization is on, e.g. `team`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plan: Option<String>,
+
+    /// Override the plan's semantic caching
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub semantic_cache: Option<bool>,
+
+    /// Override the plan's file search (vector stores and the `file_search`
+    /// tool)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_search: Option<bool>,
+
+    /// Override the plan's batch API access (files uploaded with
+    /// `purpose=batch`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch_api: Option<bool>,
+
+    /// Override the plan's project limit (0 = unlimited)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_projects: Option<u32>,
+
+    /// Override the plan's active API key limit (0 = unlimited)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_api_keys: Option<u32>,
+}
+
+impl OrgEntitlements {
+    pub fn validate(&self, config: &EntitlementsConfig) -> Result<(), String> {
+        if self == &Self::default() {
+            return Err(
+                "no plan or overrides set; delete the entitlements to use the default plan"
+                    .to_string(),
+            );
+        }
+        if let Some(plan) = &self.plan
+            && !config.plans.contains_key(plan)
+        {
+            return Err(format!(
+                "unknown plan '{plan}'; configured plans: {}",
+                config.plan_names().join(", ")
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// What an organization may use once its plan and overrides are combined.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct Entitlements {
+    /// Plan the organization is on
+    pub plan: String,
+    pub semantic_cache: bool,
+    pub file_search: bool,
+    pub batch_api: bool,
+    /// Projects the organization may have (0 = unlimited)
+    pub max_projects: u32,
+    /// Active API keys attributed to the organization (0 = unlimited)
+    pub max_api_keys: u32,
+}
+
+impl Entitlements {
+    /// Combine the organization's plan with its overrides. A plan that is no
+    /// longer configured falls back to the default plan.
+    pub fn resolve(config: &EntitlementsConfig, org: Option<&OrgEntitlements>) -> Self {
+        let plan_name = org
+            .and_then(|o| o.plan.as_deref())
+            .filter(|p| config.plans.contains_key(*p))
+            .unwrap_or(config.default_plan.as_str());
+        let plan = config.plans.get(plan_name).cloned().unwrap_or_default();
+        let overrides = org.cloned().unwrap_or_default();
+
+        Self {
+            plan: plan_name.to_string(),
+            semantic_cache: overrides.semantic_cache.unwrap_or(plan.semantic_cache),
+            file_search: overrides.file_search.unwrap_or(plan.file_search),
+            batch_api: overrides.batch_api.unwrap_or(plan.batch_api),
+            max_projects: overrides.max_projects.unwrap_or(plan.max_projects),
+            max_api_keys: overrides.max_api_keys.unwrap_or(plan.max_api_keys),
+        }
+    }
+
+    pub fn allows(&self, feature: EntitledFeature) -> bool {
+        match feature {
+            EntitledFeature::SemanticCache => self.semantic_cache,
+            EntitledFeature::FileSearch => self.file_search,
+            EntitledFeature::BatchApi => self.batch_api,
+        }
+    }
+
+    /// Check `feature`, returning why it's unavailable.
+    pub fn check(&self, feature: EntitledFeature) -> Result<(), String> {
+        if self.allows(feature) {
+            return Ok(());
+        }
+        Err(format!(
+            "{} is not included in the organization's '{}' plan",
+            feature.label(),
+            self.plan
+        ))
+    }
+
+    /// Check that the organization may create another project when it has
+    /// `projects` already.
+    pub fn check_projects(&self, projects: i64) -> Result<(), String> {
+        check_limit(self.max_projects, projects, "projects", &self.plan)
+    }
+
+    /// Check that the organization may create another API key when it has
+    /// `api_keys` active ones.
+    pub fn check_api_keys(&self, api_keys: i64) -> Result<(), String> {
+        check_limit(self.max_api_keys, api_keys, "API keys", &self.plan)
+    }
+}
+
+fn check_limit(max: u32, current: i64, what: &str, plan: &str) -> Result<(), String> {
+    if max > 0 && current >= max as i64 {
+        return Err(format!(
+            "The organization's '{plan}' plan allows at most {max} {what}"
+        ));
+    }
+    Ok(())
+}
+
+/// Usage counted against an organization's limits.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct EntitlementUsage {
+    pub projects: i64,
+    pub api_keys: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> EntitlementsConfig {
+        EntitlementsConfig {
+            enabled: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_resolve() {
+        let config = config();
+
+        let free = Entitlements::resolve(&config, None);
+        assert_eq!(free.plan, "free");
+        assert!(free.check(EntitledFeature::FileSearch).is_err());
+        assert!(free.check_projects(2).is_ok());
+        assert!(free.check_projects(3).is_err());
+
+        let team = Entitlements::resolve(
+            &config,
+            Some(&OrgEntitlements {
+                plan: Some("team".into()),
+                batch_api: Some(true),
+                max_api_keys: Some(0),
+                ..Default::default()
+            }),
+        );
+        assert_eq!(team.plan, "team");
+        assert!(team.allows(EntitledFeature::SemanticCache));
+        assert!(team.allows(EntitledFeature::BatchApi));
+        assert!(team.check_api_keys(10_000).is_ok());
+        assert_eq!(team.max_projects, 25);
+
+        // A plan removed from the config falls back to the default plan
+        let removed = Entitlements::resolve(
+            &config,
+            Some(&OrgEntitlements {
+                plan: Some("legacy".into()),
+                ..Default::default()
+            }),
+        );
+        assert_eq!(removed.plan, "free");
+    }
+
+    #[test]
+    fn test_validate() {
+        let config = config();
+        assert!(OrgEntitlements::default().validate(&config).is_err());
+        assert!(
+            OrgEntitlements {
+                plan: Some("gold".into()),
+                ..Default::default()
+            }
+            .validate(&config)
+            .is_err()
+        );
+        assert!(
+            OrgEntitlements {
+                file_search: Some(true),
+                ..Default::default()
+            }
+            .validate(&config)
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_denial_message() {
+        let free = Entitlements::resolve(&config(), None);
+        assert_eq!(
+            free.check(EntitledFeature::BatchApi).unwrap_err(),
+            "The batch API is not included in the organization's 'free' plan"
+        );
+        assert_eq!(
+            free.check_api_keys(5).unwrap_err(),
+            "The organization's 'free' plan allows at most 5 API keys"
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::DailyModelSpend;
+use crate::providers::{circuit_breaker::CircuitState, health_check::HealthStatus};
+
+/// Summary a satellite gateway pushes to its federation hub.
+///
+/// `usage` holds complete daily totals, so a hub that receives the same day
+/// twice simply replaces the earlier numbers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct FederationReport {
+    /// Satellite ID; must match the ID the hub has configured for the token.
+    pub gateway_id: String,
+    pub region: Option<String>,
+    /// Satellite gateway version
+    pub version: String,
+    pub generated_at: DateTime<Utc>,
+    /// Daily per-model usage totals
+    #[serde(default)]
+    pub usage: Vec<FederatedUsage>,
+    /// Provider health at the time of the report
+    #[serde(default)]
+    pub providers: Vec<FederatedProviderHealth>,
+}
+
+/// One day of usage for one model on a satellite.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct FederatedUsage {
+    pub date: NaiveDate,
+    pub model: String,
+    /// Total cost in microcents (1/1,000,000 of a dollar)
+    pub total_cost_microcents: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub total_tokens: i64,
+    pub request_count: i64,
+}
+
+impl From<DailyModelSpend> for FederatedUsage {
+    fn from(spend: DailyModelSpend) -> Self {
+        Self {
+            date: spend.date,
+            model: spend.model,
+            total_cost_microcents: spend.total_cost_microcents,
+            input_tokens: spend.input_tokens,
+            output_tokens: spend.output_tokens,
+            total_tokens: spend.total_tokens,
+            request_count: spend.request_count,
+        }
+    }
+}
+
+/// Health of one provider on a satellite, combining the active health check
+/// (if enabled) with circuit breaker state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct FederatedProviderHealth {
+    pub provider: String,
+    #[serde(default)]
+    pub status: HealthStatus,
+    /// Latency of the last health check in milliseconds
+    pub latency_ms: Option<u64>,
+    pub circuit_state: Option<CircuitState>,
+}
+
+/// A satellite gateway as last seen by the hub.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct FederatedGateway {
+    pub id: String,
+    pub region: Option<String>,
+    pub version: String,
+    /// Provider health from the latest report
+    pub providers: Vec<FederatedProviderHealth>,
+    pub first_report_at: DateTime<Utc>,
+    pub last_report_at: DateTime<Utc>,
+}
+
+/// Stored usage row on the hub.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct FederatedUsageRow {
+    pub gateway_id: String,
+    #[serde(flatten)]
+    pub usage: FederatedUsage,
+}
+
+/// Summed usage counters.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct FederatedUsageTotals {
+    /// Total cost in microcents (1/1,000,000 of a dollar)
+    pub total_cost_microcents: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub total_tokens: i64,
+    pub request_count: i64,
+}
+
+impl FederatedUsageTotals {
+    pub fn add(&mut self, usage: &FederatedUsage) {
+        self.total_cost_microcents += usage.total_cost_microcents;
+        self.input_tokens += usage.input_tokens;
+        self.output_tokens += usage.output_tokens;
+        self.total_tokens += usage.total_tokens;
+        self.request_count += usage.request_count;
+    }
+}
+
+/// Usage totals for one satellite gateway.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct FederatedGatewayUsage {
+    pub gateway_id: String,
+    #[serde(flatten)]
+    pub totals: FederatedUsageTotals,
+}
+
+/// Usage totals for one model across all satellites.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct FederatedModelUsage {
+    pub model: String,
+    #[serde(flatten)]
+    pub totals: FederatedUsageTotals,
+}
+
+/// Usage totals for one day across all satellites.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct FederatedDailyUsage {
+    pub date: NaiveDate,
+    #[serde(flatten)]
+    pub totals: FederatedUsageTotals,
+}
+
+/// Usage across all reporting satellites for a date range.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct FederatedUsageSummary {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub totals: FederatedUsageTotals,
+    /// Per-gateway totals, highest cost first
+    pub by_gateway: Vec<FederatedGatewayUsage>,
+    /// Per-model totals, highest cost first
+    pub by_model: Vec<FederatedModelUsage>,
+    /// Per-day totals, oldest first
+    pub by_date: Vec<FederatedDailyUsage>,
+}
@@ -0,0 +1,72 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A fine-tuning job started through `/v1/fine_tuning/jobs`, recording who
+/// owns it and the provider's job it proxies.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FineTuningJobRecord {
+    pub id: Uuid,
+    /// Name of the provider running the job
+    pub provider: String,
+    /// The provider's job ID
+    pub provider_job_id: String,
+    pub org_id: Uuid,
+    pub project_id: Option<Uuid>,
+    pub team_id: Option<Uuid>,
+    pub user_id: Option<Uuid>,
+    pub api_key_id: Option<Uuid>,
+    pub service_account_id: Option<Uuid>,
+    /// Base model as sent to the provider
+    pub model: String,
+    /// Training file as the client gave it
+    pub training_file: String,
+    pub validation_file: Option<String>,
+    /// Last status the provider reported
+    pub status: String,
+    pub fine_tuned_model: Option<String>,
+    pub trained_tokens: Option<i64>,
+    /// When the job's usage was recorded and its model registered. Set once,
+    /// so a job that succeeds is only processed by one node.
+    pub completed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl FineTuningJobRecord {
+    /// Public ID of the job, e.g. `ftjob-550e8400-e29b-41d4-a716-446655440000`.
+    pub fn public_id(&self) -> String {
+        format!("ftjob-{}", self.id)
+    }
+
+    /// Parse a public job ID back into the record ID.
+    pub fn parse_public_id(id: &str) -> Option<Uuid> {
+        id.strip_prefix("ftjob-")
+            .and_then(|uuid| Uuid::parse_str(uuid).ok())
+    }
+}
+
+/// Record of a newly started fine-tuning job.
+#[derive(Debug, Clone)]
+pub struct CreateFineTuningJobRecord {
+    pub provider: String,
+    pub provider_job_id: String,
+    pub org_id: Uuid,
+    pub project_id: Option<Uuid>,
+    pub team_id: Option<Uuid>,
+    pub user_id: Option<Uuid>,
+    pub api_key_id: Option<Uuid>,
+    pub service_account_id: Option<Uuid>,
+    pub model: String,
+    pub training_file: String,
+    pub validation_file: Option<String>,
+    pub status: String,
+}
+
+/// Latest state of a job as reported by its provider.
+#[derive(Debug, Clone)]
+pub struct FineTuningJobStatus {
+    pub status: String,
+    pub fine_tuned_model: Option<String>,
+    pub trained_tokens: Option<i64>,
+}
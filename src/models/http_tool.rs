@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize, Serializer};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::config::HttpToolConfig;
+
+/// Placeholder returned in place of header values, which may hold
+/// credentials.
+const REDACTED_HEADER_VALUE: &str = "**REDACTED**";
+
+fn redact_header_values<S: Serializer>(
+    headers: &HashMap<String, String>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(headers.keys().map(|name| (name, REDACTED_HEADER_VALUE)))
+}
+
+/// A custom HTTP tool registered for an organization through the admin API.
+/// Requests made in the organization opt in by declaring a function tool
+/// with the same name, exactly like `[[features.server_tools.http_tools]]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct HttpTool {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    /// Function name the model calls (unique per organization)
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Endpoint that receives a POST with the call's arguments
+    pub url: String,
+    /// Extra request headers. Values are write-only and returned redacted.
+    #[serde(serialize_with = "redact_header_values")]
+    pub headers: HashMap<String, String>,
+    /// JSON Schema for the arguments
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "utoipa", schema(value_type = Option<Object>))]
+    pub parameters: Option<serde_json::Value>,
+    /// Per-call timeout in seconds
+    pub timeout_secs: i32,
+    /// Maximum response body size passed back to the model
+    pub max_response_bytes: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl HttpTool {
+    /// The tool as the executor runs it.
+    pub fn to_config(&self) -> HttpToolConfig {
+        HttpToolConfig {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            url: self.url.clone(),
+            headers: self.headers.clone(),
+            parameters: self.parameters.clone(),
+            timeout_secs: self.timeout_secs as u64,
+            max_response_bytes: self.max_response_bytes as usize,
+        }
+    }
+}
+
+/// Request to register an HTTP tool
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CreateHttpTool {
+    /// Function name the model calls. Must be `[A-Za-z0-9_-]{1,64}`.
+    #[validate(length(min = 1, max = 64))]
+    pub name: String,
+    #[validate(length(max = 1000))]
+    pub description: Option<String>,
+    /// Endpoint that receives a POST with the call's arguments
+    #[validate(length(min = 1, max = 2048))]
+    pub url: String,
+    /// Extra request headers, e.g. credentials for the endpoint
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// JSON Schema for the arguments. Defaults to an empty object schema.
+    #[cfg_attr(feature = "utoipa", schema(value_type = Option<Object>))]
+    pub parameters: Option<serde_json::Value>,
+    /// Per-call timeout in seconds (default 30)
+    #[serde(default = "default_timeout_secs")]
+    #[validate(range(min = 1, max = 300))]
+    pub timeout_secs: i32,
+    /// Maximum response body size passed back to the model (default 65536)
+    #[serde(default = "default_max_response_bytes")]
+    #[validate(range(min = 1, max = 10_485_760))]
+    pub max_response_bytes: i32,
+}
+
+fn default_timeout_secs() -> i32 {
+    30
+}
+
+fn default_max_response_bytes() -> i32 {
+    65_536
+}
+
+/// Request to update an HTTP tool. Omitted fields are left unchanged;
+/// `headers` replaces the whole set.
+#[derive(Debug, Clone, Default, Deserialize, Validate)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct UpdateHttpTool {
+    #[validate(length(min = 1, max = 64))]
+    pub name: Option<String>,
+    #[validate(length(max = 1000))]
+    pub description: Option<String>,
+    #[validate(length(min = 1, max = 2048))]
+    pub url: Option<String>,
+    pub headers: Option<HashMap<String, String>>,
+    #[cfg_attr(feature = "utoipa", schema(value_type = Option<Object>))]
+    pub parameters: Option<serde_json::Value>,
+    #[validate(range(min = 1, max = 300))]
+    pub timeout_secs: Option<i32>,
+    #[validate(range(min = 1, max = 10_485_760))]
+    pub max_response_bytes: Option<i32>,
+}
@@ -0,0 +1,54 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Whether the request holding an idempotency key has finished.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdempotencyStatus {
+    InProgress,
+    Completed,
+}
+
+impl IdempotencyStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::InProgress => "in_progress",
+            Self::Completed => "completed",
+        }
+    }
+}
+
+impl std::str::FromStr for IdempotencyStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "in_progress" => Ok(Self::InProgress),
+            "completed" => Ok(Self::Completed),
+            _ => Err(format!("Invalid idempotency status: {}", s)),
+        }
+    }
+}
+
+/// A response stored for replay to repeats of its idempotency key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdempotentResponse {
+    pub status: u16,
+    pub content_type: Option<String>,
+    pub body: Vec<u8>,
+}
+
+/// A claimed idempotency key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdempotencyRecord {
+    /// Hash of the caller, endpoint and client-supplied key
+    pub key: String,
+    /// SHA-256 of the request body, to catch a key reused for another request
+    pub request_hash: String,
+    pub status: IdempotencyStatus,
+    /// The response to replay; `None` while in progress, or when the
+    /// response was too large to store
+    pub response: Option<IdempotentResponse>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
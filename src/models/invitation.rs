@@ -0,0 +1,153 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Where an invitation is in its lifecycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum InvitationStatus {
+    /// Sent and waiting to be accepted.
+    Pending,
+    /// Accepted by the invited user on sign-in.
+    Accepted,
+    /// Revoked by an administrator.
+    Revoked,
+    /// Not accepted before `expires_at`. Resending renews it.
+    Expired,
+}
+
+impl InvitationStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Accepted => "accepted",
+            Self::Revoked => "revoked",
+            Self::Expired => "expired",
+        }
+    }
+
+    /// Status of a stored invitation. Expiry isn't stored: a pending
+    /// invitation past `expires_at` is reported as expired.
+    pub fn from_stored(
+        status: &str,
+        expires_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<Self, String> {
+        match status {
+            "pending" if expires_at <= now => Ok(Self::Expired),
+            "pending" => Ok(Self::Pending),
+            "accepted" => Ok(Self::Accepted),
+            "revoked" => Ok(Self::Revoked),
+            _ => Err(format!("Invalid invitation status: {}", status)),
+        }
+    }
+}
+
+/// An email address invited to join an organization, and optionally one of
+/// its teams.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct Invitation {
+    /// Unique identifier
+    pub id: Uuid,
+    /// Organization the invitee joins
+    pub org_id: Uuid,
+    /// Team the invitee joins, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub team_id: Option<Uuid>,
+    /// Invited email address (lowercased)
+    pub email: String,
+    /// Role granted on acceptance. For team invitations this is the team
+    /// role; the invitee joins the organization as a `member`.
+    pub role: String,
+    /// Current status
+    pub status: InvitationStatus,
+    /// User who created the invitation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invited_by: Option<Uuid>,
+    /// When the invite link stops working
+    pub expires_at: DateTime<Utc>,
+    /// Number of times the invitation has been delivered
+    pub send_count: i32,
+    /// When the invitation was last delivered
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_sent_at: Option<DateTime<Utc>>,
+    /// When the invitation was accepted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accepted_at: Option<DateTime<Utc>>,
+    /// User who accepted the invitation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accepted_by: Option<Uuid>,
+    /// When the invitation was revoked
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request to invite an email address to an organization
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CreateInvitation {
+    /// Email address to invite
+    #[validate(email, length(max = 255))]
+    pub email: String,
+    /// Role to grant (defaults to 'member')
+    #[validate(length(min = 1, max = 64))]
+    #[serde(default = "default_role")]
+    pub role: String,
+    /// Slug of a team in the organization to add the invitee to
+    #[serde(default)]
+    pub team_slug: Option<String>,
+}
+
+fn default_role() -> String {
+    "member".to_string()
+}
+
+/// A new invitation as stored, with the hash of its token.
+#[derive(Debug, Clone)]
+pub struct NewInvitation {
+    pub org_id: Uuid,
+    pub team_id: Option<Uuid>,
+    pub email: String,
+    pub role: String,
+    pub invited_by: Option<Uuid>,
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_status_from_stored() {
+        let now = Utc::now();
+        let later = now + Duration::hours(1);
+        let earlier = now - Duration::hours(1);
+
+        assert_eq!(
+            InvitationStatus::from_stored("pending", later, now),
+            Ok(InvitationStatus::Pending)
+        );
+        assert_eq!(
+            InvitationStatus::from_stored("pending", earlier, now),
+            Ok(InvitationStatus::Expired)
+        );
+        // Accepted and revoked invitations don't expire
+        assert_eq!(
+            InvitationStatus::from_stored("accepted", earlier, now),
+            Ok(InvitationStatus::Accepted)
+        );
+        assert_eq!(
+            InvitationStatus::from_stored("revoked", earlier, now),
+            Ok(InvitationStatus::Revoked)
+        );
+        assert!(InvitationStatus::from_stored("expired", later, now).is_err());
+    }
+}
@@ -0,0 +1,16 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// The node currently leading a cluster-wide background job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct JobLeader {
+    /// Job name, e.g. `retention` or `vector_store_cleanup`
+    pub job: String,
+    /// Node that last ran the job
+    pub node_id: String,
+    /// When this node took over the job
+    pub acquired_at: DateTime<Utc>,
+    /// When this node last ran the job
+    pub last_run_at: DateTime<Utc>,
+}
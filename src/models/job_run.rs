@@ -0,0 +1,99 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// What started a background job run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum JobTrigger {
+    /// The job's regular interval.
+    Scheduled,
+    /// `POST /admin/v1/jobs/{name}/run`.
+    Manual,
+}
+
+impl JobTrigger {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Scheduled => "scheduled",
+            Self::Manual => "manual",
+        }
+    }
+}
+
+impl std::str::FromStr for JobTrigger {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "scheduled" => Ok(Self::Scheduled),
+            "manual" => Ok(Self::Manual),
+            _ => Err(format!("Invalid job trigger: {}", s)),
+        }
+    }
+}
+
+/// Outcome of a background job run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum JobRunStatus {
+    Succeeded,
+    Failed,
+}
+
+impl JobRunStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Succeeded => "succeeded",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+impl std::str::FromStr for JobRunStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "succeeded" => Ok(Self::Succeeded),
+            "failed" => Ok(Self::Failed),
+            _ => Err(format!("Invalid job run status: {}", s)),
+        }
+    }
+}
+
+/// One finished run of a background job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct JobRun {
+    pub id: Uuid,
+    /// Job name, e.g. `retention`
+    pub job: String,
+    /// Node that ran the job
+    pub node_id: String,
+    pub triggered_by: JobTrigger,
+    pub status: JobRunStatus,
+    /// What the run did, e.g. how many records it deleted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub duration_ms: i64,
+}
+
+/// Input for recording a job run.
+#[derive(Debug, Clone)]
+pub struct CreateJobRun {
+    pub job: String,
+    pub node_id: String,
+    pub triggered_by: JobTrigger,
+    pub status: JobRunStatus,
+    pub summary: Option<String>,
+    pub error: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+}
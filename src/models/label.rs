@@ -0,0 +1,168 @@
+//! Labels on admin resources.
+//!
+//! Organizations, projects, API keys, dynamic providers and templates carry
+//! free-form `key=value` labels (e.g. `env=prod`, `team=search`) so tooling
+//! can group and find them without encoding that information in slugs or
+//! names. List endpoints for these resources accept a [`LabelSelector`] via
+//! `?label=env=prod,tier=web`.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Most labels on a single resource.
+pub const MAX_LABELS: usize = 32;
+/// Longest accepted label key.
+pub const MAX_LABEL_KEY_LENGTH: usize = 63;
+/// Longest accepted label value.
+pub const MAX_LABEL_VALUE_LENGTH: usize = 128;
+
+/// Whether `key` is a valid label key: 1-63 lowercase ASCII letters, digits,
+/// `_`, `-`, `.` or `/`, starting with a letter or digit.
+pub fn is_valid_label_key(key: &str) -> bool {
+    key.len() <= MAX_LABEL_KEY_LENGTH
+        && key
+            .bytes()
+            .next()
+            .is_some_and(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+        && key.bytes().all(|b| {
+            b.is_ascii_lowercase() || b.is_ascii_digit() || matches!(b, b'_' | b'-' | b'.' | b'/')
+        })
+}
+
+/// Whether `value` is a valid label value: 1-128 characters, without commas
+/// (which separate selector requirements) or control characters.
+pub fn is_valid_label_value(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= MAX_LABEL_VALUE_LENGTH
+        && !value.chars().any(|c| c == ',' || c.is_control())
+}
+
+/// Validate a resource's labels.
+pub fn validate_labels(labels: &BTreeMap<String, String>) -> Result<(), String> {
+    if labels.len() > MAX_LABELS {
+        return Err(format!("at most {MAX_LABELS} labels are allowed"));
+    }
+    for (key, value) in labels {
+        if !is_valid_label_key(key) {
+            return Err(format!(
+                "invalid label key '{key}': use 1-{MAX_LABEL_KEY_LENGTH} lowercase letters, \
+                 digits, '_', '-', '.' or '/'"
+            ));
+        }
+        if !is_valid_label_value(value) {
+            return Err(format!(
+                "invalid value for label '{key}': use 1-{MAX_LABEL_VALUE_LENGTH} characters \
+                 without commas"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Replace all labels on a resource. An empty map removes them.
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct SetLabels {
+    /// Labels to set, e.g. `{"env": "prod", "team": "search"}`
+    pub labels: BTreeMap<String, String>,
+}
+
+/// Label requirements a resource must all satisfy to be listed.
+///
+/// Parsed from a comma-separated list of `key=value` pairs. An empty
+/// selector matches everything.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct LabelSelector(BTreeMap<String, String>);
+
+impl LabelSelector {
+    /// Parse `env=prod,tier=web`. Keys are lowercased; whitespace around
+    /// keys and values is ignored.
+    pub fn parse(selector: &str) -> Result<Self, String> {
+        let mut requirements = BTreeMap::new();
+        for requirement in selector.split(',') {
+            let requirement = requirement.trim();
+            if requirement.is_empty() {
+                continue;
+            }
+            let Some((key, value)) = requirement.split_once('=') else {
+                return Err(format!(
+                    "invalid label selector '{requirement}': expected key=value"
+                ));
+            };
+            let key = key.trim().to_ascii_lowercase();
+            let value = value.trim();
+            if !is_valid_label_key(&key) || !is_valid_label_value(value) {
+                return Err(format!("invalid label selector '{requirement}'"));
+            }
+            if let Some(existing) = requirements.get(&key)
+                && existing != value
+            {
+                return Err(format!(
+                    "label selector requires '{key}' to be both '{existing}' and '{value}'"
+                ));
+            }
+            requirements.insert(key, value.to_string());
+        }
+        Ok(Self(requirements))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Whether `labels` satisfy every requirement.
+    pub fn matches(&self, labels: &BTreeMap<String, String>) -> bool {
+        self.0.iter().all(|(k, v)| labels.get(k) == Some(v))
+    }
+
+    /// The requirements as a JSON object, for matching against a `labels`
+    /// column in SQL.
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(&self.0).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_labels() {
+        assert!(validate_labels(&labels(&[("env", "prod"), ("app.io/tier", "Web 1")])).is_ok());
+        assert!(validate_labels(&labels(&[("Env", "prod")])).is_err());
+        assert!(validate_labels(&labels(&[("-env", "prod")])).is_err());
+        assert!(validate_labels(&labels(&[("env", "")])).is_err());
+        assert!(validate_labels(&labels(&[("env", "a,b")])).is_err());
+
+        let too_many = (0..=MAX_LABELS)
+            .map(|i| (format!("k{i}"), "v".to_string()))
+            .collect();
+        assert!(validate_labels(&too_many).is_err());
+    }
+
+    #[test]
+    fn test_parse_selector() {
+        let selector = LabelSelector::parse(" Env=prod , tier=web,").unwrap();
+        assert!(selector.matches(&labels(&[("env", "prod"), ("tier", "web"), ("x", "y")])));
+        assert!(!selector.matches(&labels(&[("env", "prod")])));
+        assert_eq!(
+            selector.to_json(),
+            serde_json::json!({"env": "prod", "tier": "web"})
+        );
+
+        assert!(LabelSelector::parse("").unwrap().is_empty());
+        assert!(LabelSelector::parse("env").is_err());
+        assert!(LabelSelector::parse("env=").is_err());
+        assert!(LabelSelector::parse("env=prod,env=dev").is_err());
+        assert!(LabelSelector::parse("env=prod,env=prod").is_ok());
+    }
+}
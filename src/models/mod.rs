@@ -1,60 +1,126 @@
 mod access_review;
+mod admin_passkey;
+mod admin_totp;
+mod agent;
 mod api_key;
 mod api_key_gen;
 mod attribute_filter;
 mod audit_log;
+mod bundle;
+mod client_cert_mapping;
 mod conversation;
 #[cfg(feature = "sso")]
 mod domain_verification;
 mod dynamic_provider;
+mod email_log;
+mod entitlement;
+mod federation;
+mod fine_tuning_job;
+mod http_tool;
+mod idempotency_key;
+mod invitation;
+mod job_leader;
+mod job_run;
+mod label;
+mod model_access;
+mod model_catalog_entry;
+mod model_degradation;
 mod model_pricing;
 mod oauth_authorization_code;
 mod org_rbac_policy;
+mod org_request_policy;
 #[cfg(feature = "sso")]
 mod org_sso_config;
 mod organization;
+mod parameter_governance;
 mod prefixed_id;
 mod project;
+mod provider_file;
+mod provider_probe;
+mod provider_settings;
+mod provider_usage_import;
 mod ranking_options;
+mod request_defaults;
+mod role_elevation;
+mod scheduled_report;
+mod scheduled_task;
 #[cfg(feature = "sso")]
 mod scim;
 mod service_account;
+mod shadow_result;
 mod skill;
 #[cfg(feature = "sso")]
 mod sso_group_mapping;
+#[cfg(feature = "sso")]
+mod sso_role_rule;
 mod team;
 mod template;
 mod usage;
+mod usage_anomaly;
 mod user;
 mod validators;
 mod vector_store;
+mod vector_store_sync;
 
 pub use access_review::*;
+pub use admin_passkey::*;
+pub use admin_totp::*;
+pub use agent::*;
 pub use api_key::*;
 pub use api_key_gen::*;
 pub use attribute_filter::*;
 pub use audit_log::*;
+pub use bundle::*;
+pub use client_cert_mapping::*;
 pub use conversation::*;
 #[cfg(feature = "sso")]
 pub use domain_verification::*;
 pub use dynamic_provider::*;
+pub use email_log::*;
+pub use entitlement::*;
+pub use federation::*;
+pub use fine_tuning_job::*;
+pub use http_tool::*;
+pub use idempotency_key::*;
+pub use invitation::*;
+pub use job_leader::*;
+pub use job_run::*;
+pub use label::*;
+pub use model_access::*;
+pub use model_catalog_entry::*;
+pub use model_degradation::*;
 pub use model_pricing::*;
 pub use oauth_authorization_code::*;
 pub use org_rbac_policy::*;
+pub use org_request_policy::*;
 #[cfg(feature = "sso")]
 pub use org_sso_config::*;
 pub use organization::*;
+pub use parameter_governance::*;
 pub use prefixed_id::*;
 pub use project::*;
+pub use provider_file::*;
+pub use provider_probe::*;
+pub use provider_settings::*;
+pub use provider_usage_import::*;
 pub use ranking_options::*;
+pub use request_defaults::*;
+pub use role_elevation::*;
+pub use scheduled_report::*;
+pub use scheduled_task::*;
 #[cfg(feature = "sso")]
 pub use scim::*;
 pub use service_account::*;
+pub use shadow_result::*;
 pub use skill::*;
 #[cfg(feature = "sso")]
 pub use sso_group_mapping::*;
+#[cfg(feature = "sso")]
+pub use sso_role_rule::*;
 pub use team::*;
 pub use template::*;
 pub use usage::*;
+pub use usage_anomaly::*;
 pub use user::*;
 pub use vector_store::*;
+pub use vector_store_sync::*;
@@ -0,0 +1,205 @@
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of entries in each list of a model access policy.
+pub const MAX_MODEL_ACCESS_ENTRIES: usize = 500;
+
+/// Which models and providers an organization's or project's callers may
+/// use.
+///
+/// Managed via `/admin/v1/organizations/{slug}/model-access` and the project
+/// equivalent. A request must pass both the organization and the project
+/// policy. Deny lists win over allow lists; an empty allow list allows
+/// everything not denied.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct ModelAccessPolicy {
+    /// Model patterns callers may use; a trailing `*` matches a prefix.
+    /// Matched against both the requested model (`openai/gpt-4o`) and the
+    /// model name without its provider prefix (`gpt-4o`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_models: Vec<String>,
+
+    /// Model patterns callers may not use
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub denied_models: Vec<String>,
+
+    /// Providers callers may use, by name
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_providers: Vec<String>,
+
+    /// Providers callers may not use, by name
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub denied_providers: Vec<String>,
+}
+
+impl ModelAccessPolicy {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.allowed_models.is_empty()
+            && self.denied_models.is_empty()
+            && self.allowed_providers.is_empty()
+            && self.denied_providers.is_empty()
+        {
+            return Err(
+                "policy sets no allow or deny lists; delete the policy to lift it".to_string(),
+            );
+        }
+        for (field, entries) in [
+            ("allowed_models", &self.allowed_models),
+            ("denied_models", &self.denied_models),
+            ("allowed_providers", &self.allowed_providers),
+            ("denied_providers", &self.denied_providers),
+        ] {
+            if entries.len() > MAX_MODEL_ACCESS_ENTRIES {
+                return Err(format!(
+                    "{field} may have at most {MAX_MODEL_ACCESS_ENTRIES} entries, got {}",
+                    entries.len()
+                ));
+            }
+        }
+        for (field, patterns) in [
+            ("allowed_models", &self.allowed_models),
+            ("denied_models", &self.denied_models),
+        ] {
+            super::validate_model_patterns(patterns)
+                .map_err(|invalid| format!("{field}: invalid patterns: {}", invalid.join(", ")))?;
+        }
+        for (field, providers) in [
+            ("allowed_providers", &self.allowed_providers),
+            ("denied_providers", &self.denied_providers),
+        ] {
+            if let Some(invalid) = providers.iter().find(|p| p.is_empty() || p.contains('/')) {
+                return Err(format!("{field}: invalid provider name '{invalid}'"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check a request for `model`, routed to `provider`.
+    ///
+    /// `model` is the model string as the caller sent it; `provider` is the
+    /// provider it routes to. Returns why the request is denied.
+    pub fn check(&self, provider: &str, model: &str) -> Result<(), String> {
+        if self.denied_providers.iter().any(|p| p == provider) {
+            return Err(format!("provider '{provider}' is not allowed"));
+        }
+        if !self.allowed_providers.is_empty()
+            && !self.allowed_providers.iter().any(|p| p == provider)
+        {
+            return Err(format!("provider '{provider}' is not allowed"));
+        }
+        if self.denied_models.iter().any(|p| model_matches(model, p)) {
+            return Err(format!("model '{model}' is not allowed"));
+        }
+        if !self.allowed_models.is_empty()
+            && !self.allowed_models.iter().any(|p| model_matches(model, p))
+        {
+            return Err(format!("model '{model}' is not allowed"));
+        }
+        Ok(())
+    }
+
+    /// Add `model` to a non-empty allow list that doesn't already match it,
+    /// such as a newly fine-tuned model. Returns whether the policy changed;
+    /// a full allow list is left alone.
+    pub fn allow_model(&mut self, model: &str) -> bool {
+        if self.allowed_models.is_empty()
+            || self.allowed_models.len() >= MAX_MODEL_ACCESS_ENTRIES
+            || self.allowed_models.iter().any(|p| model_matches(model, p))
+        {
+            return false;
+        }
+        self.allowed_models.push(model.to_string());
+        true
+    }
+}
+
+/// Match `pattern` against `model` with and without its provider prefix.
+fn model_matches(model: &str, pattern: &str) -> bool {
+    super::model_matches_pattern(model, pattern)
+        || model
+            .split_once('/')
+            .is_some_and(|(_, bare)| super::model_matches_pattern(bare, pattern))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert!(
+            ModelAccessPolicy {
+                allowed_models: vec!["gpt-4*".into()],
+                denied_providers: vec!["bedrock".into()],
+                ..Default::default()
+            }
+            .validate()
+            .is_ok()
+        );
+        assert!(ModelAccessPolicy::default().validate().is_err());
+        assert!(
+            ModelAccessPolicy {
+                denied_models: vec!["*".into()],
+                ..Default::default()
+            }
+            .validate()
+            .is_err()
+        );
+        assert!(
+            ModelAccessPolicy {
+                allowed_providers: vec!["openai/gpt-4o".into()],
+                ..Default::default()
+            }
+            .validate()
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let policy = ModelAccessPolicy {
+            allowed_models: vec!["gpt-4*".into(), "claude-sonnet-4".into()],
+            denied_models: vec!["gpt-4-32k".into()],
+            denied_providers: vec!["azure".into()],
+            ..Default::default()
+        };
+        assert!(policy.check("openai", "gpt-4o").is_ok());
+        assert!(policy.check("openai", "openai/gpt-4o").is_ok());
+        assert!(policy.check("anthropic", "claude-sonnet-4").is_ok());
+        assert!(policy.check("openai", "gpt-4-32k").is_err());
+        assert!(policy.check("openai", "o3").is_err());
+        assert!(policy.check("azure", "azure/gpt-4o").is_err());
+
+        let providers_only = ModelAccessPolicy {
+            allowed_providers: vec!["anthropic".into()],
+            ..Default::default()
+        };
+        assert!(providers_only.check("anthropic", "claude-opus-4").is_ok());
+        assert!(providers_only.check("openai", "gpt-4o").is_err());
+    }
+
+    #[test]
+    fn test_allow_model() {
+        let mut policy = ModelAccessPolicy {
+            allowed_models: vec!["gpt-4o*".into()],
+            ..Default::default()
+        };
+        assert!(!policy.allow_model("gpt-4o-mini"));
+        assert!(policy.allow_model("ft:gpt-4.1:acme::abc"));
+        assert!(
+            policy
+                .check("openai", "openai/ft:gpt-4.1:acme::abc")
+                .is_ok()
+        );
+        assert_eq!(policy.allowed_models.len(), 2);
+
+        // An empty allow list already allows everything
+        let mut open = ModelAccessPolicy {
+            denied_models: vec!["o3".into()],
+            ..Default::default()
+        };
+        assert!(!open.allow_model("ft:gpt-4.1:acme::abc"));
+        assert!(open.allowed_models.is_empty());
+    }
+}
@@ -0,0 +1,135 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::catalog::{CatalogPricing, ModelCapabilities, ModelLimits, ModelModalities};
+
+/// Catalog metadata for a model, in the shape the catalog uses for models.dev
+/// entries. Omitted capabilities default to unsupported.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CatalogEntryMetadata {
+    /// Model family (e.g. "llama")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub family: Option<String>,
+    #[serde(default)]
+    pub capabilities: ModelCapabilities,
+    #[serde(default)]
+    pub modalities: ModelModalities,
+    #[serde(default)]
+    pub limits: ModelLimits,
+    /// Pricing in dollars per 1M tokens, used for cost tracking when no
+    /// pricing is configured for the model. Without it, pricing comes from
+    /// the models.dev entry of the same model, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(custom(function = "validate_pricing"))]
+    pub pricing: Option<CatalogPricing>,
+    /// Knowledge cutoff date (YYYY-MM or YYYY-MM-DD)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub knowledge_cutoff: Option<String>,
+    /// Release date (YYYY-MM-DD)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub release_date: Option<String>,
+    /// Date the model was deprecated (YYYY-MM-DD)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecation_date: Option<String>,
+    /// Date the model stops being served (YYYY-MM-DD)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sunset_date: Option<String>,
+    #[serde(default)]
+    pub open_weights: bool,
+}
+
+fn validate_pricing(pricing: &CatalogPricing) -> Result<(), ValidationError> {
+    let prices = [pricing.input, pricing.output]
+        .into_iter()
+        .chain(pricing.reasoning)
+        .chain(pricing.cache_read)
+        .chain(pricing.cache_write);
+    for price in prices {
+        if !price.is_finite() || price < 0.0 {
+            return Err(ValidationError::new("pricing")
+                .with_message("prices must be non-negative numbers".into()));
+        }
+    }
+    Ok(())
+}
+
+/// A model catalog entry added through `/admin/v1/model-catalog`. It takes
+/// precedence over the models.dev entry for the same provider and model.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ModelCatalogEntry {
+    pub id: Uuid,
+    /// Catalog provider ID (e.g. "openai", or a provider's `catalog_provider`)
+    pub provider: String,
+    /// Model ID as sent to the provider
+    pub model: String,
+    #[serde(flatten)]
+    pub metadata: CatalogEntryMetadata,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request to add a model catalog entry.
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CreateModelCatalogEntry {
+    /// Catalog provider ID (e.g. "openai", or a provider's `catalog_provider`)
+    #[validate(length(min = 1, max = 128))]
+    pub provider: String,
+    /// Model ID as sent to the provider
+    #[validate(length(min = 1, max = 255))]
+    pub model: String,
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub metadata: CatalogEntryMetadata,
+}
+
+/// Request to replace the metadata of a model catalog entry. Omitted fields
+/// are cleared.
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct UpdateModelCatalogEntry {
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub metadata: CatalogEntryMetadata,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_partial_metadata_deserializes() {
+        let input: CreateModelCatalogEntry = serde_json::from_value(json!({
+            "provider": "vllm",
+            "model": "llama-3.1-8b-ft",
+            "capabilities": {"tool_call": true},
+            "limits": {"context_length": 131072},
+            "pricing": {"input": 0.1, "output": 0.2}
+        }))
+        .unwrap();
+
+        assert!(input.validate().is_ok());
+        assert!(input.metadata.capabilities.tool_call);
+        assert!(!input.metadata.capabilities.vision);
+        assert_eq!(input.metadata.limits.context_length, Some(131072));
+        assert!(input.metadata.modalities.input.is_empty());
+    }
+
+    #[test]
+    fn test_negative_pricing_rejected() {
+        let input: CreateModelCatalogEntry = serde_json::from_value(json!({
+            "provider": "vllm",
+            "model": "m",
+            "pricing": {"input": -1.0, "output": 0.2}
+        }))
+        .unwrap();
+
+        assert!(input.validate().is_err());
+    }
+}
@@ -0,0 +1,206 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Maximum number of ladders in a degradation policy.
+pub const MAX_DEGRADATION_LADDERS: usize = 50;
+
+/// Maximum number of rungs below a ladder's model.
+pub const MAX_DEGRADATION_RUNGS: usize = 5;
+
+/// Maximum number of API keys a degradation policy can be limited to.
+pub const MAX_DEGRADATION_API_KEYS: usize = 500;
+
+/// When a request is moved down its model's ladder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum DegradationTrigger {
+    /// The provider answered `429 Too Many Requests`
+    RateLimited,
+    /// The API key's spend passed the budget warning threshold
+    OverBudget,
+    /// The model's provider has an open circuit breaker
+    CircuitOpen,
+}
+
+impl DegradationTrigger {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::RateLimited => "rate_limited",
+            Self::OverBudget => "over_budget",
+            Self::CircuitOpen => "circuit_open",
+        }
+    }
+
+    fn all() -> Vec<Self> {
+        vec![Self::RateLimited, Self::OverBudget, Self::CircuitOpen]
+    }
+}
+
+/// Cheaper models to fall back to for a premium model, in order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct DegradationLadder {
+    /// Requested model pattern; a trailing `*` matches a prefix. Matched
+    /// against both the requested model (`openai/gpt-4o`) and the model name
+    /// without its provider prefix (`gpt-4o`).
+    pub model: String,
+
+    /// Models to downgrade to, most preferred first, as they'd be sent in
+    /// a request (`openai/gpt-4o-mini`)
+    pub downgrades: Vec<String>,
+}
+
+/// An organization's opt-in to downgrading premium models under pressure.
+///
+/// Managed via `/admin/v1/organizations/{slug}/model-degradation`. Without a
+/// policy, requests are never downgraded.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct ModelDegradationPolicy {
+    /// Ladders to follow; the first whose `model` matches the request is used
+    pub ladders: Vec<DegradationLadder>,
+
+    /// Conditions that move a request down its ladder. Defaults to all of
+    /// them.
+    #[serde(default = "DegradationTrigger::all")]
+    pub triggers: Vec<DegradationTrigger>,
+
+    /// API keys the policy applies to. Empty applies it to every caller in
+    /// the organization.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub api_keys: Vec<Uuid>,
+}
+
+impl ModelDegradationPolicy {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.ladders.is_empty() {
+            return Err("policy has no ladders; delete the policy to opt out".to_string());
+        }
+        if self.ladders.len() > MAX_DEGRADATION_LADDERS {
+            return Err(format!(
+                "ladders may have at most {MAX_DEGRADATION_LADDERS} entries, got {}",
+                self.ladders.len()
+            ));
+        }
+        if self.triggers.is_empty() {
+            return Err("triggers must not be empty".to_string());
+        }
+        if self.api_keys.len() > MAX_DEGRADATION_API_KEYS {
+            return Err(format!(
+                "api_keys may have at most {MAX_DEGRADATION_API_KEYS} entries, got {}",
+                self.api_keys.len()
+            ));
+        }
+        for (idx, ladder) in self.ladders.iter().enumerate() {
+            super::validate_model_patterns(std::slice::from_ref(&ladder.model))
+                .map_err(|_| format!("ladders[{idx}].model: invalid pattern '{}'", ladder.model))?;
+            if ladder.downgrades.is_empty() || ladder.downgrades.len() > MAX_DEGRADATION_RUNGS {
+                return Err(format!(
+                    "ladders[{idx}].downgrades must have 1 to {MAX_DEGRADATION_RUNGS} models"
+                ));
+            }
+            for model in &ladder.downgrades {
+                if model.is_empty() || model.contains('*') {
+                    return Err(format!(
+                        "ladders[{idx}].downgrades: '{model}' is not a model name"
+                    ));
+                }
+                if model_matches(model, &ladder.model) {
+                    return Err(format!(
+                        "ladders[{idx}].downgrades: '{model}' matches the ladder's own model"
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the policy covers a caller, by API key (`None` for callers
+    /// without one).
+    pub fn applies_to(&self, api_key_id: Option<Uuid>) -> bool {
+        self.api_keys.is_empty() || api_key_id.is_some_and(|id| self.api_keys.contains(&id))
+    }
+
+    /// Whether `trigger` is enabled.
+    pub fn triggers_on(&self, trigger: DegradationTrigger) -> bool {
+        self.triggers.contains(&trigger)
+    }
+
+    /// The ladder for a requested model, if any.
+    pub fn ladder_for(&self, model: &str) -> Option<&DegradationLadder> {
+        self.ladders
+            .iter()
+            .find(|ladder| model_matches(model, &ladder.model))
+    }
+}
+
+/// Match `pattern` against `model` with and without its provider prefix.
+fn model_matches(model: &str, pattern: &str) -> bool {
+    super::model_matches_pattern(model, pattern)
+        || model
+            .split_once('/')
+            .is_some_and(|(_, bare)| super::model_matches_pattern(bare, pattern))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(model: &str, downgrades: &[&str]) -> ModelDegradationPolicy {
+        ModelDegradationPolicy {
+            ladders: vec![DegradationLadder {
+                model: model.into(),
+                downgrades: downgrades.iter().map(|m| m.to_string()).collect(),
+            }],
+            triggers: DegradationTrigger::all(),
+            api_keys: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(
+            policy("openai/gpt-4o", &["openai/gpt-4o-mini"])
+                .validate()
+                .is_ok()
+        );
+        assert!(policy("gpt-4o", &[]).validate().is_err());
+        assert!(policy("gpt-4o", &["openai/gpt-4o"]).validate().is_err());
+        assert!(policy("gpt-4o", &["gpt-4o-mini*"]).validate().is_err());
+        assert!(policy("*", &["gpt-4o-mini"]).validate().is_err());
+
+        let mut no_triggers = policy("gpt-4o", &["gpt-4o-mini"]);
+        no_triggers.triggers.clear();
+        assert!(no_triggers.validate().is_err());
+
+        let defaulted: ModelDegradationPolicy = serde_json::from_value(serde_json::json!({
+            "ladders": [{"model": "gpt-4o", "downgrades": ["gpt-4o-mini"]}]
+        }))
+        .unwrap();
+        assert_eq!(defaulted.triggers, DegradationTrigger::all());
+    }
+
+    #[test]
+    fn test_ladder_for() {
+        let policy = policy("claude-opus-4*", &["anthropic/claude-sonnet-4"]);
+        assert!(policy.ladder_for("anthropic/claude-opus-4-1").is_some());
+        assert!(policy.ladder_for("claude-opus-4").is_some());
+        assert!(policy.ladder_for("anthropic/claude-sonnet-4").is_none());
+    }
+
+    #[test]
+    fn test_applies_to() {
+        let key = Uuid::new_v4();
+        let mut policy = policy("gpt-4o", &["gpt-4o-mini"]);
+        assert!(policy.applies_to(None));
+        assert!(policy.applies_to(Some(key)));
+
+        policy.api_keys = vec![key];
+        assert!(policy.applies_to(Some(key)));
+        assert!(!policy.applies_to(Some(Uuid::new_v4())));
+        assert!(!policy.applies_to(None));
+    }
+}
@@ -0,0 +1,196 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+/// What a request policy does when its condition matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum RequestPolicyAction {
+    /// Let the request through and stop evaluating lower-priority policies
+    Allow,
+    /// Reject the request with `403 Forbidden` (default - fail closed)
+    #[default]
+    Deny,
+    /// Overwrite request fields with the policy's `modifications` and keep
+    /// evaluating lower-priority policies
+    Modify,
+}
+
+impl RequestPolicyAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Allow => "allow",
+            Self::Deny => "deny",
+            Self::Modify => "modify",
+        }
+    }
+}
+
+impl std::fmt::Display for RequestPolicyAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for RequestPolicyAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "allow" => Ok(Self::Allow),
+            "deny" => Ok(Self::Deny),
+            "modify" => Ok(Self::Modify),
+            _ => Err(format!("Invalid request policy action: {}", s)),
+        }
+    }
+}
+
+/// Organization request policy.
+///
+/// A CEL expression evaluated against every data-plane request made with the
+/// organization's credentials. Enabled policies run in priority order
+/// (highest first): the first matching `allow` or `deny` decides the request,
+/// while matching `modify` policies rewrite it and let evaluation continue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct OrgRequestPolicy {
+    /// Unique identifier for this policy
+    pub id: Uuid,
+    /// Organization this policy belongs to
+    pub org_id: Uuid,
+    /// Human-readable name for this policy (unique per org)
+    pub name: String,
+    /// Optional description of what this policy does
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// CEL expression over `request`, `subject` and `time`
+    pub condition: String,
+    /// Action taken when the condition matches
+    pub action: RequestPolicyAction,
+    /// Top-level request fields to set when a `modify` policy matches
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modifications: Option<serde_json::Value>,
+    /// Message returned to the caller when a `deny` policy matches
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Priority for evaluation order (higher = evaluated first)
+    pub priority: i32,
+    /// Whether this policy is active
+    pub enabled: bool,
+    /// When this policy was created
+    pub created_at: DateTime<Utc>,
+    /// When this policy was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request to create a new organization request policy.
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CreateOrgRequestPolicy {
+    /// Human-readable name for this policy (unique per org)
+    #[validate(length(min = 1, max = 128))]
+    pub name: String,
+
+    /// Optional description of what this policy does
+    #[validate(length(max = 1024))]
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// CEL expression that must evaluate to true for the policy to apply
+    #[validate(length(min = 1, max = 4096))]
+    pub condition: String,
+
+    /// Action taken when the condition matches (defaults to 'deny')
+    #[serde(default)]
+    pub action: RequestPolicyAction,
+
+    /// Top-level request fields to set. Required for `modify`, must be
+    /// omitted otherwise.
+    #[serde(default)]
+    pub modifications: Option<serde_json::Value>,
+
+    /// Message returned to the caller when a `deny` policy matches
+    #[validate(length(max = 1024))]
+    #[serde(default)]
+    pub message: Option<String>,
+
+    /// Priority for evaluation order (higher = evaluated first, defaults to 0)
+    /// Valid range: -1000 to 1000
+    #[validate(range(min = -1000, max = 1000))]
+    #[serde(default)]
+    pub priority: i32,
+
+    /// Whether this policy is active (defaults to true)
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for CreateOrgRequestPolicy {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            description: None,
+            condition: String::new(),
+            action: RequestPolicyAction::default(),
+            modifications: None,
+            message: None,
+            priority: 0,
+            enabled: true,
+        }
+    }
+}
+
+/// Request to update an existing organization request policy.
+///
+/// All fields are optional - only provided fields will be updated.
+#[derive(Debug, Clone, Default, Deserialize, Validate)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct UpdateOrgRequestPolicy {
+    /// Update the policy name
+    #[validate(length(min = 1, max = 128))]
+    pub name: Option<String>,
+
+    /// Update the description (set to null to remove)
+    #[validate(length(max = 1024))]
+    #[serde(default, deserialize_with = "deserialize_optional")]
+    pub description: Option<Option<String>>,
+
+    /// Update the CEL condition
+    #[validate(length(min = 1, max = 4096))]
+    pub condition: Option<String>,
+
+    /// Update the action
+    pub action: Option<RequestPolicyAction>,
+
+    /// Update the modifications (set to null to remove)
+    #[serde(default, deserialize_with = "deserialize_optional")]
+    pub modifications: Option<Option<serde_json::Value>>,
+
+    /// Update the deny message (set to null to remove)
+    #[validate(length(max = 1024))]
+    #[serde(default, deserialize_with = "deserialize_optional")]
+    pub message: Option<Option<String>>,
+
+    /// Update the priority (valid range: -1000 to 1000)
+    #[validate(range(min = -1000, max = 1000))]
+    pub priority: Option<i32>,
+
+    /// Update the enabled state
+    pub enabled: Option<bool>,
+}
+
+/// Distinguishes a missing field (`None`, leave unchanged) from an explicit
+/// `null` (`Some(None)`, clear the value).
+fn deserialize_optional<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Ok(Some(Option::deserialize(deserializer)?))
+}
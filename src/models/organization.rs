@@ -1,9 +1,17 @@
+use std::{collections::BTreeMap, net::IpAddr};
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use validator::Validate;
 
-use super::validators::SLUG_REGEX;
+use super::{
+    ConversationSummarySettings, ModelAccessPolicy, ModelDegradationPolicy, OrgEntitlements,
+    ParameterGovernance, RequestDefaults, ip_matches_entry,
+    request_defaults::deserialize_optional_request_defaults, validate_ip_allowlist,
+    validators::SLUG_REGEX,
+};
+use crate::config::DataResidencyPolicy;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
@@ -11,10 +19,80 @@ pub struct Organization {
     pub id: Uuid,
     pub slug: String,
     pub name: String,
+    /// Data residency policy applied to every request made on behalf of
+    /// this organization
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_residency: Option<DataResidencyPolicy>,
+    /// Default model and parameters applied when clients omit them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_defaults: Option<RequestDefaults>,
+    /// Background conversation title and summary generation settings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conversation_summaries: Option<ConversationSummarySettings>,
+    /// Network ranges the organization's API keys may be used from. Managed
+    /// via `/admin/v1/organizations/{slug}/network-policy`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network_policy: Option<OrgNetworkPolicy>,
+    /// Parameter bounds enforced on the organization's requests. Managed via
+    /// `/admin/v1/organizations/{slug}/parameter-governance`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parameter_governance: Option<ParameterGovernance>,
+    /// Models and providers the organization's callers may use. Managed via
+    /// `/admin/v1/organizations/{slug}/model-access`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_access: Option<ModelAccessPolicy>,
+    /// Ladders of cheaper models the organization's requests may be
+    /// downgraded along. Managed via
+    /// `/admin/v1/organizations/{slug}/model-degradation`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_degradation: Option<ModelDegradationPolicy>,
+    /// Plan and per-feature overrides deciding which features the
+    /// organization may use. Managed via
+    /// `/admin/v1/organizations/{slug}/entitlements`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entitlements: Option<OrgEntitlements>,
+    /// Free-form labels, e.g. `{"env": "prod"}`. Managed via
+    /// `/admin/v1/organizations/{slug}/labels`.
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// Organization-wide network policy for API key usage.
+///
+/// Applies to every API key attributed to the organization (org, team,
+/// project and service account keys, and tokens exchanged from them), on top
+/// of each key's own `ip_allowlist`: a request must satisfy both.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct OrgNetworkPolicy {
+    /// IP addresses or CIDR ranges requests may come from, e.g.
+    /// `["10.0.0.0/8", "203.0.113.7"]`
+    pub ip_allowlist: Vec<String>,
+}
+
+impl OrgNetworkPolicy {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.ip_allowlist.is_empty() {
+            return Err(
+                "ip_allowlist must contain at least one entry; delete the policy to lift it"
+                    .to_string(),
+            );
+        }
+        validate_ip_allowlist(&self.ip_allowlist)
+            .map_err(|invalid| format!("invalid ip_allowlist entries: {}", invalid.join(", ")))
+    }
+
+    /// Whether `ip` falls within one of the allowed ranges.
+    pub fn is_ip_allowed(&self, ip: IpAddr) -> bool {
+        self.ip_allowlist
+            .iter()
+            .any(|entry| ip_matches_entry(ip, entry))
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Validate)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub struct CreateOrganization {
@@ -32,4 +110,69 @@ pub struct UpdateOrganization {
     /// New display name
     #[validate(length(min = 1, max = 255))]
     pub name: Option<String>,
+    /// Data residency policy (set to null to remove)
+    #[serde(default, deserialize_with = "deserialize_optional_policy")]
+    pub data_residency: Option<Option<DataResidencyPolicy>>,
+    /// Default model and parameters (set to null to remove)
+    #[serde(default, deserialize_with = "deserialize_optional_request_defaults")]
+    pub request_defaults: Option<Option<RequestDefaults>>,
+    /// Conversation title and summary generation settings (set to null to
+    /// fall back to the gateway default)
+    #[serde(default, deserialize_with = "deserialize_optional_summary_settings")]
+    pub conversation_summaries: Option<Option<ConversationSummarySettings>>,
+}
+
+/// Custom deserializer for Option<Option<DataResidencyPolicy>> to distinguish between:
+/// - Field not present in JSON -> None (don't update)
+/// - Field present as null -> Some(None) (remove the policy)
+/// - Field present with value -> Some(Some(policy)) (replace the policy)
+fn deserialize_optional_policy<'de, D>(
+    deserializer: D,
+) -> Result<Option<Option<DataResidencyPolicy>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(Some(Option::deserialize(deserializer)?))
+}
+
+/// Same three-way semantics as [`deserialize_optional_policy`].
+fn deserialize_optional_summary_settings<'de, D>(
+    deserializer: D,
+) -> Result<Option<Option<ConversationSummarySettings>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(Some(Option::deserialize(deserializer)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(entries: &[&str]) -> OrgNetworkPolicy {
+        OrgNetworkPolicy {
+            ip_allowlist: entries.iter().map(|e| e.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_network_policy_validate() {
+        assert!(
+            policy(&["10.0.0.0/8", "203.0.113.7", "2001:db8::/32"])
+                .validate()
+                .is_ok()
+        );
+        assert!(policy(&[]).validate().is_err());
+        let err = policy(&["10.0.0.0/8", "not-an-ip"]).validate().unwrap_err();
+        assert!(err.contains("not-an-ip"));
+    }
+
+    #[test]
+    fn test_network_policy_is_ip_allowed() {
+        let policy = policy(&["10.0.0.0/8", "203.0.113.7"]);
+        assert!(policy.is_ip_allowed("10.1.2.3".parse().unwrap()));
+        assert!(policy.is_ip_allowed("203.0.113.7".parse().unwrap()));
+        assert!(!policy.is_ip_allowed("203.0.113.8".parse().unwrap()));
+        assert!(!policy.is_ip_allowed("::1".parse().unwrap()));
+    }
 }
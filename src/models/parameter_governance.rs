@@ -0,0 +1,220 @@
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of rules on one organization or project.
+pub const MAX_GOVERNANCE_RULES: usize = 50;
+
+/// Request fields that can't be forbidden, since no request is valid
+/// without them.
+const REQUIRED_FIELDS: &[&str] = &["model", "models", "messages", "input", "prompt"];
+
+/// Parameter bounds enforced on an organization's or project's data-plane
+/// requests.
+///
+/// Managed via `/admin/v1/organizations/{slug}/parameter-governance` and the
+/// project equivalent. Organization rules run first, then project rules, so
+/// a project can tighten its organization's bounds but not loosen them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct ParameterGovernance {
+    /// Rules applied in order to every request whose model they match
+    pub rules: Vec<ParameterGovernanceRule>,
+}
+
+/// One set of parameter bounds.
+///
+/// Bounds only apply to parameters the caller sent: a request without
+/// `temperature` is left for the provider default.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct ParameterGovernanceRule {
+    /// Model patterns this rule applies to; a trailing `*` matches a prefix.
+    /// Empty matches every model.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub models: Vec<String>,
+
+    /// Upper bound for `max_tokens`, `max_completion_tokens` and
+    /// `max_output_tokens`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u64>,
+
+    /// Lower bound for `temperature`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_temperature: Option<f64>,
+
+    /// Upper bound for `temperature`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_temperature: Option<f64>,
+
+    /// Top-level request fields callers may not send, e.g. `logit_bias`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub forbidden_params: Vec<String>,
+
+    /// System prompt added when a chat request has no system or developer
+    /// message, or a Responses request has no `instructions`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_system_prompt: Option<String>,
+
+    /// What to do with a request that breaks the bounds
+    #[serde(default)]
+    pub on_violation: GovernanceViolationAction,
+}
+
+/// How a governance rule handles a request outside its bounds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum GovernanceViolationAction {
+    /// Bring the request within bounds (cap values, drop forbidden fields)
+    /// and forward it
+    #[default]
+    Clamp,
+    /// Reject the request with `400`
+    Reject,
+}
+
+impl ParameterGovernance {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.rules.is_empty() {
+            return Err(
+                "rules must contain at least one rule; delete the governance policy to lift it"
+                    .to_string(),
+            );
+        }
+        if self.rules.len() > MAX_GOVERNANCE_RULES {
+            return Err(format!(
+                "at most {MAX_GOVERNANCE_RULES} rules are allowed, got {}",
+                self.rules.len()
+            ));
+        }
+        for (i, rule) in self.rules.iter().enumerate() {
+            rule.validate().map_err(|e| format!("rules[{i}]: {e}"))?;
+        }
+        Ok(())
+    }
+}
+
+impl ParameterGovernanceRule {
+    fn validate(&self) -> Result<(), String> {
+        if self.max_tokens.is_none()
+            && self.min_temperature.is_none()
+            && self.max_temperature.is_none()
+            && self.forbidden_params.is_empty()
+            && self.default_system_prompt.is_none()
+        {
+            return Err("rule sets no bounds, forbidden parameters or default prompt".to_string());
+        }
+        super::validate_model_patterns(&self.models)
+            .map_err(|invalid| format!("invalid model patterns: {}", invalid.join(", ")))?;
+        if self.max_tokens == Some(0) {
+            return Err("max_tokens must be greater than 0".to_string());
+        }
+        for temperature in [self.min_temperature, self.max_temperature]
+            .into_iter()
+            .flatten()
+        {
+            if !(0.0..=2.0).contains(&temperature) {
+                return Err(format!(
+                    "temperature bound {temperature} is outside 0.0-2.0"
+                ));
+            }
+        }
+        if let (Some(min), Some(max)) = (self.min_temperature, self.max_temperature)
+            && min > max
+        {
+            return Err(format!(
+                "min_temperature ({min}) is greater than max_temperature ({max})"
+            ));
+        }
+        for param in &self.forbidden_params {
+            if param.is_empty() {
+                return Err("forbidden_params entries must not be empty".to_string());
+            }
+            if REQUIRED_FIELDS.contains(&param.as_str()) {
+                return Err(format!(
+                    "'{param}' is required by every request and can't be forbidden"
+                ));
+            }
+        }
+        if let Some(prompt) = &self.default_system_prompt
+            && (prompt.is_empty() || prompt.len() > 32768)
+        {
+            return Err("default_system_prompt must be 1-32768 bytes".to_string());
+        }
+        Ok(())
+    }
+
+    /// Whether this rule applies to requests for `model`.
+    pub fn matches_model(&self, model: Option<&str>) -> bool {
+        if self.models.is_empty() {
+            return true;
+        }
+        model.is_some_and(|model| {
+            self.models
+                .iter()
+                .any(|pattern| super::model_matches_pattern(model, pattern))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn governance(rule: ParameterGovernanceRule) -> ParameterGovernance {
+        ParameterGovernance { rules: vec![rule] }
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(
+            governance(ParameterGovernanceRule {
+                max_tokens: Some(4096),
+                min_temperature: Some(0.0),
+                max_temperature: Some(1.0),
+                forbidden_params: vec!["logit_bias".into()],
+                ..Default::default()
+            })
+            .validate()
+            .is_ok()
+        );
+        assert!(ParameterGovernance { rules: vec![] }.validate().is_err());
+        assert!(
+            governance(ParameterGovernanceRule::default())
+                .validate()
+                .is_err()
+        );
+
+        let err = governance(ParameterGovernanceRule {
+            min_temperature: Some(1.5),
+            max_temperature: Some(0.5),
+            ..Default::default()
+        })
+        .validate()
+        .unwrap_err();
+        assert!(err.starts_with("rules[0]:"), "{err}");
+
+        let err = governance(ParameterGovernanceRule {
+            forbidden_params: vec!["messages".into()],
+            ..Default::default()
+        })
+        .validate()
+        .unwrap_err();
+        assert!(err.contains("messages"), "{err}");
+    }
+
+    #[test]
+    fn test_matches_model() {
+        let rule = ParameterGovernanceRule {
+            models: vec!["gpt-4*".into(), "claude-sonnet-4".into()],
+            max_tokens: Some(1),
+            ..Default::default()
+        };
+        assert!(rule.matches_model(Some("gpt-4o")));
+        assert!(rule.matches_model(Some("claude-sonnet-4")));
+        assert!(!rule.matches_model(Some("claude-opus-4")));
+        assert!(!rule.matches_model(None));
+        assert!(ParameterGovernanceRule::default().matches_model(None));
+    }
+}
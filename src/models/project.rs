@@ -1,9 +1,14 @@
+use std::collections::BTreeMap;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use validator::Validate;
 
-use super::validators::SLUG_REGEX;
+use super::{
+    ModelAccessPolicy, ParameterGovernance, RequestDefaults,
+    request_defaults::deserialize_optional_request_defaults, validators::SLUG_REGEX,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
@@ -15,6 +20,24 @@ pub struct Project {
     pub team_id: Option<Uuid>,
     pub slug: String,
     pub name: String,
+    /// Default model and parameters applied when clients omit them.
+    /// Overrides the organization's defaults field by field.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_defaults: Option<RequestDefaults>,
+    /// Parameter bounds enforced on the project's requests, after the
+    /// organization's. Managed via
+    /// `/admin/v1/organizations/{org_slug}/projects/{project_slug}/parameter-governance`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parameter_governance: Option<ParameterGovernance>,
+    /// Models and providers the project's callers may use, checked in
+    /// addition to the organization's policy. Managed via
+    /// `/admin/v1/organizations/{org_slug}/projects/{project_slug}/model-access`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_access: Option<ModelAccessPolicy>,
+    /// Free-form labels, e.g. `{"env": "prod"}`. Managed via
+    /// `/admin/v1/organizations/{org_slug}/projects/{project_slug}/labels`.
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -41,6 +64,9 @@ pub struct UpdateProject {
     /// Team to assign the project to (use null to remove team assignment)
     #[serde(default, deserialize_with = "deserialize_optional_team_id")]
     pub team_id: Option<Option<Uuid>>,
+    /// Default model and parameters (set to null to remove)
+    #[serde(default, deserialize_with = "deserialize_optional_request_defaults")]
+    pub request_defaults: Option<Option<RequestDefaults>>,
 }
 
 /// Custom deserializer that handles:
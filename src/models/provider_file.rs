@@ -0,0 +1,17 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A copy of a gateway file uploaded to a provider, such as training data
+/// for a fine-tuning job.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProviderFile {
+    pub id: Uuid,
+    /// The gateway file that was copied
+    pub file_id: Uuid,
+    /// Name of the provider holding the copy
+    pub provider: String,
+    /// The provider's ID for the copy
+    pub provider_file_id: String,
+    pub created_at: DateTime<Utc>,
+}
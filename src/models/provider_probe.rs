@@ -0,0 +1,63 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Outcome of one synthetic canary completion against a provider model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ProviderProbeResult {
+    pub id: Uuid,
+    pub provider: String,
+    pub model: String,
+    /// The provider returned a completion.
+    pub success: bool,
+    /// The completion contained the expected answer.
+    pub correct: bool,
+    /// End-to-end latency of the completion request.
+    pub latency_ms: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_tokens: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_tokens: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Input for recording a probe result.
+#[derive(Debug, Clone)]
+pub struct CreateProviderProbeResult {
+    pub provider: String,
+    pub model: String,
+    pub success: bool,
+    pub correct: bool,
+    pub latency_ms: i64,
+    pub input_tokens: Option<i64>,
+    pub output_tokens: Option<i64>,
+    pub error: Option<String>,
+}
+
+/// Probe results for one model aggregated over a time bucket.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ProbeBucketStats {
+    /// Start of the time bucket
+    pub bucket_start: DateTime<Utc>,
+    /// Model the probes ran against
+    pub model: String,
+    /// Probes run in this bucket
+    pub probe_count: i64,
+    /// Probes that returned a completion
+    pub success_count: i64,
+    /// Probes whose completion contained the expected answer
+    pub correct_count: i64,
+    /// Average latency of successful probes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_latency_ms: Option<f64>,
+    /// Slowest successful probe
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_latency_ms: Option<i64>,
+    /// Average output tokens of successful probes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_output_tokens: Option<f64>,
+}
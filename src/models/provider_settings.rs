@@ -0,0 +1,310 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::config::{CircuitBreakerConfig, ProviderConfig, RetryConfig};
+
+/// Circuit breaker parameters that override a provider's
+/// `circuit_breaker` section. Unset fields keep the file's value.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct CircuitBreakerSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub failure_threshold: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub open_timeout_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub success_threshold: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1.0))]
+    pub backoff_multiplier: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_open_timeout_secs: Option<u64>,
+}
+
+/// Retry parameters that override a provider's `retry` section. Unset
+/// fields keep the file's value.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct RetrySettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(max = 10))]
+    pub max_retries: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub initial_delay_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_delay_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1.0))]
+    pub backoff_multiplier: Option<f64>,
+}
+
+impl From<&CircuitBreakerConfig> for CircuitBreakerSettings {
+    fn from(config: &CircuitBreakerConfig) -> Self {
+        Self {
+            enabled: Some(config.enabled),
+            failure_threshold: Some(config.failure_threshold),
+            open_timeout_secs: Some(config.open_timeout_secs),
+            success_threshold: Some(config.success_threshold),
+            backoff_multiplier: Some(config.backoff_multiplier),
+            max_open_timeout_secs: Some(config.max_open_timeout_secs),
+        }
+    }
+}
+
+impl From<&RetryConfig> for RetrySettings {
+    fn from(config: &RetryConfig) -> Self {
+        Self {
+            enabled: Some(config.enabled),
+            max_retries: Some(config.max_retries),
+            initial_delay_ms: Some(config.initial_delay_ms),
+            max_delay_ms: Some(config.max_delay_ms),
+            backoff_multiplier: Some(config.backoff_multiplier),
+        }
+    }
+}
+
+/// Runtime settings for a provider defined in the config file, set through
+/// `/admin/v1/providers/{name}/settings`. They take precedence over the
+/// file on every node; unset fields keep the file's value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ProviderSettings {
+    pub id: Uuid,
+    /// Name of the provider in the config file
+    pub provider_name: String,
+    /// Set to false to stop routing requests to the provider
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub circuit_breaker: Option<CircuitBreakerSettings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetrySettings>,
+    /// Overrides `fair_queue.max_concurrent`. Only valid for providers with
+    /// a fair queue.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_concurrent: Option<u32>,
+    /// User who last changed the settings
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ProviderSettings {
+    /// Whether requests may be routed to the provider.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.unwrap_or(true)
+    }
+
+    /// Whether these settings change how the circuit breaker or fair queue
+    /// built from the file config behave, compared to `other`.
+    pub fn changes_limits(&self, other: Option<&ProviderSettings>) -> bool {
+        match other {
+            Some(other) => {
+                self.circuit_breaker != other.circuit_breaker
+                    || self.max_concurrent != other.max_concurrent
+            }
+            None => self.circuit_breaker.is_some() || self.max_concurrent.is_some(),
+        }
+    }
+
+    /// Apply the settings on top of the file config.
+    pub fn apply_to(&self, config: &mut ProviderConfig) {
+        if let Some(settings) = &self.circuit_breaker {
+            let cb = config.circuit_breaker_config_mut();
+            if let Some(v) = settings.enabled {
+                cb.enabled = v;
+            }
+            if let Some(v) = settings.failure_threshold {
+                cb.failure_threshold = v;
+            }
+            if let Some(v) = settings.open_timeout_secs {
+                cb.open_timeout_secs = v;
+            }
+            if let Some(v) = settings.success_threshold {
+                cb.success_threshold = v;
+            }
+            if let Some(v) = settings.backoff_multiplier {
+                cb.backoff_multiplier = v;
+            }
+            if let Some(v) = settings.max_open_timeout_secs {
+                cb.max_open_timeout_secs = v;
+            }
+        }
+        if let Some(settings) = &self.retry {
+            let retry = config.retry_config_mut();
+            if let Some(v) = settings.enabled {
+                retry.enabled = v;
+            }
+            if let Some(v) = settings.max_retries {
+                retry.max_retries = v;
+            }
+            if let Some(v) = settings.initial_delay_ms {
+                retry.initial_delay_ms = v;
+            }
+            if let Some(v) = settings.max_delay_ms {
+                retry.max_delay_ms = v;
+            }
+            if let Some(v) = settings.backoff_multiplier {
+                retry.backoff_multiplier = v;
+            }
+        }
+        if let Some(max_concurrent) = self.max_concurrent
+            && let Some(fair_queue) = config.fair_queue_config_mut()
+        {
+            fair_queue.max_concurrent = max_concurrent;
+        }
+    }
+}
+
+/// Request to set a provider's runtime settings. Replaces any settings set
+/// before; omitted fields fall back to the config file.
+#[derive(Debug, Clone, Default, Deserialize, Validate)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct UpdateProviderSettings {
+    /// Set to false to stop routing requests to the provider
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    #[serde(default)]
+    #[validate(nested)]
+    pub circuit_breaker: Option<CircuitBreakerSettings>,
+    #[serde(default)]
+    #[validate(nested)]
+    pub retry: Option<RetrySettings>,
+    /// Overrides `fair_queue.max_concurrent`
+    #[serde(default)]
+    #[validate(range(min = 1, max = 100000))]
+    pub max_concurrent: Option<u32>,
+}
+
+impl UpdateProviderSettings {
+    /// Check the settings against the provider's file config.
+    pub fn validate_for(&self, config: &ProviderConfig) -> Result<(), String> {
+        if self.max_concurrent.is_some() && config.fair_queue_config().is_none() {
+            return Err(
+                "max_concurrent can only be set for providers with a fair_queue section".into(),
+            );
+        }
+        if let Some(cb) = &self.circuit_breaker {
+            let file = config.circuit_breaker_config();
+            let open = cb.open_timeout_secs.unwrap_or(file.open_timeout_secs);
+            let max_open = cb
+                .max_open_timeout_secs
+                .unwrap_or(file.max_open_timeout_secs);
+            if max_open < open {
+                return Err(
+                    "circuit_breaker.max_open_timeout_secs must be at least open_timeout_secs"
+                        .into(),
+                );
+            }
+        }
+        if let Some(retry) = &self.retry {
+            let file = config.retry_config();
+            let initial = retry.initial_delay_ms.unwrap_or(file.initial_delay_ms);
+            let max = retry.max_delay_ms.unwrap_or(file.max_delay_ms);
+            if max < initial {
+                return Err("retry.max_delay_ms must be at least initial_delay_ms".into());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider(toml: &str) -> ProviderConfig {
+        toml::from_str(toml).unwrap()
+    }
+
+    fn settings(input: UpdateProviderSettings) -> ProviderSettings {
+        ProviderSettings {
+            id: Uuid::new_v4(),
+            provider_name: "openai".into(),
+            enabled: input.enabled,
+            circuit_breaker: input.circuit_breaker,
+            retry: input.retry,
+            max_concurrent: input.max_concurrent,
+            updated_by: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_apply_overrides_only_set_fields() {
+        let mut config = provider(
+            r#"
+            type = "test"
+            circuit_breaker = { enabled = true, failure_threshold = 5, open_timeout_secs = 30 }
+            retry = { max_retries = 3 }
+            fair_queue = { max_concurrent = 8 }
+            "#,
+        );
+        settings(UpdateProviderSettings {
+            circuit_breaker: Some(CircuitBreakerSettings {
+                failure_threshold: Some(2),
+                ..Default::default()
+            }),
+            retry: Some(RetrySettings {
+                enabled: Some(false),
+                ..Default::default()
+            }),
+            max_concurrent: Some(16),
+            ..Default::default()
+        })
+        .apply_to(&mut config);
+
+        let cb = config.circuit_breaker_config();
+        assert!(cb.enabled);
+        assert_eq!(cb.failure_threshold, 2);
+        assert_eq!(cb.open_timeout_secs, 30);
+        assert!(!config.retry_config().enabled);
+        assert_eq!(config.retry_config().max_retries, 3);
+        assert_eq!(config.fair_queue_config().unwrap().max_concurrent, 16);
+    }
+
+    #[test]
+    fn test_validate_for_file_config() {
+        let config = provider(r#"type = "test""#);
+        let input = UpdateProviderSettings {
+            max_concurrent: Some(4),
+            ..Default::default()
+        };
+        assert!(input.validate_for(&config).is_err());
+
+        let input = UpdateProviderSettings {
+            circuit_breaker: Some(CircuitBreakerSettings {
+                open_timeout_secs: Some(600),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert!(input.validate_for(&config).is_err());
+
+        let input = UpdateProviderSettings {
+            enabled: Some(false),
+            circuit_breaker: Some(CircuitBreakerSettings {
+                enabled: Some(true),
+                open_timeout_secs: Some(60),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert!(input.validate_for(&config).is_ok());
+    }
+}
@@ -0,0 +1,126 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Where imported provider usage came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum UsageImportSource {
+    /// OpenAI usage API or usage export
+    OpenAi,
+    /// Anthropic usage report API or console export
+    Anthropic,
+    /// Azure Cost Management export
+    Azure,
+}
+
+impl UsageImportSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::OpenAi => "openai",
+            Self::Anthropic => "anthropic",
+            Self::Azure => "azure",
+        }
+    }
+}
+
+impl std::str::FromStr for UsageImportSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "openai" => Ok(Self::OpenAi),
+            "anthropic" => Ok(Self::Anthropic),
+            "azure" => Ok(Self::Azure),
+            _ => Err(format!("Invalid usage import source: {}", s)),
+        }
+    }
+}
+
+/// How provider usage was imported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum UsageImportMethod {
+    /// Pulled from the provider's usage API
+    Api,
+    /// Uploaded as a CSV export
+    Csv,
+}
+
+impl UsageImportMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Api => "api",
+            Self::Csv => "csv",
+        }
+    }
+}
+
+impl std::str::FromStr for UsageImportMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "api" => Ok(Self::Api),
+            "csv" => Ok(Self::Csv),
+            _ => Err(format!("Invalid usage import method: {}", s)),
+        }
+    }
+}
+
+/// One import of provider-reported usage.
+///
+/// An import replaces all earlier imported usage for its provider between
+/// `start_date` and `end_date`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ProviderUsageImport {
+    pub id: Uuid,
+    /// Provider name in `[providers]`, as recorded in usage
+    pub provider: String,
+    pub source: UsageImportSource,
+    pub method: UsageImportMethod,
+    /// First day covered (inclusive)
+    pub start_date: NaiveDate,
+    /// Last day covered (inclusive)
+    pub end_date: NaiveDate,
+    /// Day and model rows imported
+    pub row_count: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Input for recording an import.
+#[derive(Debug, Clone)]
+pub struct CreateProviderUsageImport {
+    pub provider: String,
+    pub source: UsageImportSource,
+    pub method: UsageImportMethod,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+}
+
+/// Usage a provider reported for one day and model.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ImportedUsage {
+    pub date: NaiveDate,
+    pub model: String,
+    /// `None` when the source doesn't report request counts
+    pub request_count: Option<i64>,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    /// Billed cost in microcents, when the source reports it
+    pub cost_microcents: Option<i64>,
+}
+
+/// Stored imported usage row.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ImportedUsageRow {
+    pub provider: String,
+    pub import_id: Uuid,
+    #[serde(flatten)]
+    pub usage: ImportedUsage,
+}
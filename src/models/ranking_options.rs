@@ -48,6 +48,7 @@
 //! ```
 
 use serde::{Deserialize, Serialize};
+use validator::Validate;
 
 /// The ranker algorithm to use for file search.
 ///
@@ -137,19 +138,21 @@ impl std::fmt::Display for FileSearchRanker {
 /// - Higher text_weight: Favor exact keyword matches
 ///
 /// Weights are relative; they don't need to sum to 1.0.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Validate)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub struct HybridSearchOptions {
     /// The weight of the embedding (vector) search in reciprocal rank fusion.
     ///
     /// Higher values give more influence to semantic similarity matches.
     /// Default: 1.0
+    #[validate(range(min = 0.0))]
     pub embedding_weight: f64,
 
     /// The weight of the text (keyword) search in reciprocal rank fusion.
     ///
     /// Higher values give more influence to exact keyword matches.
     /// Default: 1.0
+    #[validate(range(min = 0.0))]
     pub text_weight: f64,
 }
 
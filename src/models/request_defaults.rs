@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// Default request parameters applied by the gateway when a client omits them.
+///
+/// Set on organizations and projects. Project values override organization
+/// values field by field; anything the client sends explicitly always wins.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Validate)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct RequestDefaults {
+    /// Model used when the request specifies neither `model` nor `models`
+    #[validate(length(min = 1, max = 256))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+
+    /// Sampling temperature used when the request omits `temperature`
+    #[validate(range(min = 0.0, max = 2.0))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+
+    /// System prompt used when a chat request carries no system or developer
+    /// message, or a Responses request has no `instructions`
+    #[validate(length(min = 1, max = 32768))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_prompt: Option<String>,
+
+    /// Repair attempts when a `json_schema` chat completion fails validation,
+    /// used when the request doesn't set its own (see
+    /// `[features.structured_outputs]`)
+    #[validate(range(max = 10))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub structured_output_repairs: Option<u32>,
+}
+
+impl RequestDefaults {
+    /// Layer `overrides` (e.g. project defaults) on top of `base` (e.g. org
+    /// defaults). Fields set on `overrides` win.
+    pub fn merge(base: Option<&Self>, overrides: Option<&Self>) -> Option<Self> {
+        match (base, overrides) {
+            (None, None) => None,
+            (Some(x), None) | (None, Some(x)) => Some(x.clone()),
+            (Some(b), Some(o)) => Some(Self {
+                model: o.model.clone().or_else(|| b.model.clone()),
+                temperature: o.temperature.or(b.temperature),
+                system_prompt: o.system_prompt.clone().or_else(|| b.system_prompt.clone()),
+                structured_output_repairs: o
+                    .structured_output_repairs
+                    .or(b.structured_output_repairs),
+            }),
+        }
+    }
+
+    /// Returns true if no default is set.
+    pub fn is_empty(&self) -> bool {
+        self.model.is_none()
+            && self.temperature.is_none()
+            && self.system_prompt.is_none()
+            && self.structured_output_repairs.is_none()
+    }
+}
+
+/// Custom deserializer for Option<Option<RequestDefaults>> to distinguish between:
+/// - Field not present in JSON -> None (don't update)
+/// - Field present as null -> Some(None) (remove the defaults)
+/// - Field present with value -> Some(Some(defaults)) (replace the defaults)
+pub(crate) fn deserialize_optional_request_defaults<'de, D>(
+    deserializer: D,
+) -> Result<Option<Option<RequestDefaults>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(Some(Option::deserialize(deserializer)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_project_overrides_org() {
+        let org = RequestDefaults {
+            model: Some("openai/gpt-4o".into()),
+            temperature: Some(0.2),
+            system_prompt: Some("Be concise.".into()),
+            structured_output_repairs: Some(2),
+        };
+        let project = RequestDefaults {
+            model: Some("anthropic/claude-sonnet-4".into()),
+            ..Default::default()
+        };
+        let merged = RequestDefaults::merge(Some(&org), Some(&project)).unwrap();
+        assert_eq!(merged.model.as_deref(), Some("anthropic/claude-sonnet-4"));
+        assert_eq!(merged.temperature, Some(0.2));
+        assert_eq!(merged.system_prompt.as_deref(), Some("Be concise."));
+        assert_eq!(merged.structured_output_repairs, Some(2));
+    }
+
+    #[test]
+    fn test_merge_none() {
+        assert!(RequestDefaults::merge(None, None).is_none());
+        let org = RequestDefaults {
+            temperature: Some(1.0),
+            ..Default::default()
+        };
+        assert_eq!(RequestDefaults::merge(Some(&org), None), Some(org));
+    }
+
+    #[test]
+    fn test_validate_temperature_range() {
+        let defaults = RequestDefaults {
+            temperature: Some(3.0),
+            ..Default::default()
+        };
+        assert!(defaults.validate().is_err());
+    }
+}
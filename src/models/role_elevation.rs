@@ -0,0 +1,136 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Where a role elevation is in its lifecycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ElevationStatus {
+    /// Requested and waiting for an approver.
+    Pending,
+    /// Approved; the user holds the elevated role until `expires_at`.
+    Active,
+    /// Turned down by an approver.
+    Denied,
+    /// Withdrawn while pending, or ended early while active.
+    Revoked,
+    /// Ran until `expires_at` and was reverted.
+    Expired,
+}
+
+impl ElevationStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Active => "active",
+            Self::Denied => "denied",
+            Self::Revoked => "revoked",
+            Self::Expired => "expired",
+        }
+    }
+}
+
+impl std::str::FromStr for ElevationStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(Self::Pending),
+            "active" => Ok(Self::Active),
+            "denied" => Ok(Self::Denied),
+            "revoked" => Ok(Self::Revoked),
+            "expired" => Ok(Self::Expired),
+            _ => Err(format!("Invalid elevation status: {}", s)),
+        }
+    }
+}
+
+/// A time-boxed elevation of a member's organization role (break-glass
+/// access).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct RoleElevation {
+    /// Unique identifier
+    pub id: Uuid,
+    /// Organization the role applies to
+    pub org_id: Uuid,
+    /// Member whose role is elevated
+    pub user_id: Uuid,
+    /// Role granted while the elevation is active
+    pub role: String,
+    /// Role the member held when the elevation was approved, restored when
+    /// it ends
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_role: Option<String>,
+    /// Why the access is needed
+    pub justification: String,
+    /// How long the role is held once approved
+    pub duration_hours: i32,
+    /// Current status
+    pub status: ElevationStatus,
+    /// User who filed the request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requested_by: Option<Uuid>,
+    /// User who approved or denied the request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decided_by: Option<Uuid>,
+    /// Note left by the approver
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decision_note: Option<String>,
+    /// When the request was approved or denied
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decided_at: Option<DateTime<Utc>>,
+    /// When the elevated role is reverted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// When the elevation was revoked or expired
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ended_at: Option<DateTime<Utc>>,
+    /// User who revoked the elevation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ended_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request to elevate a member's organization role
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CreateRoleElevation {
+    /// Role to hold, e.g. `admin`
+    #[validate(length(min = 1, max = 64))]
+    pub role: String,
+    /// Why the access is needed. Recorded in the audit log.
+    #[validate(length(min = 1, max = 2000))]
+    pub justification: String,
+    /// How long to hold the role once approved
+    #[validate(range(min = 1))]
+    pub duration_hours: u32,
+    /// Member to elevate. Defaults to the caller; requesting for someone
+    /// else requires permission to update the organization.
+    #[serde(default)]
+    pub user_id: Option<Uuid>,
+}
+
+/// Approver's decision on a pending elevation
+#[derive(Debug, Clone, Default, Deserialize, Validate)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ElevationDecision {
+    /// Optional note, recorded with the decision
+    #[validate(length(max = 2000))]
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+/// A new elevation request as stored.
+#[derive(Debug, Clone)]
+pub struct NewRoleElevation {
+    pub org_id: Uuid,
+    pub user_id: Uuid,
+    pub role: String,
+    pub justification: String,
+    pub duration_hours: i32,
+    pub requested_by: Option<Uuid>,
+}
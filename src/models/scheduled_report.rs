@@ -0,0 +1,89 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::config::{ReportFormat, ReportKind};
+
+/// Outcome of a single scheduled report delivery attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ReportRunStatus {
+    /// The report was rendered and accepted by the delivery channel.
+    Delivered,
+    /// Rendering or delivery failed; see `error`.
+    Failed,
+}
+
+impl ReportRunStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Delivered => "delivered",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+impl std::str::FromStr for ReportRunStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "delivered" => Ok(Self::Delivered),
+            "failed" => Ok(Self::Failed),
+            _ => Err(format!("Invalid report run status: {}", s)),
+        }
+    }
+}
+
+/// One attempt to generate and deliver a scheduled report for an
+/// organization and reporting period.
+///
+/// Every attempt is recorded, so a period that failed twice before
+/// succeeding has three rows.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ReportRun {
+    pub id: Uuid,
+    /// Name of the report definition in `[features.scheduled_reports]`.
+    pub report_name: String,
+    pub kind: ReportKind,
+    pub org_id: Uuid,
+    /// First day covered by the report (inclusive).
+    pub period_start: NaiveDate,
+    /// Last day covered by the report (inclusive).
+    pub period_end: NaiveDate,
+    pub format: ReportFormat,
+    /// Delivery channel used (`email` or `webhook`).
+    pub delivery: String,
+    pub status: ReportRunStatus,
+    /// Failure reason when `status` is `failed`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Rendered report body. Only populated when fetching a single run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Input for recording a report run.
+#[derive(Debug, Clone)]
+pub struct CreateReportRun {
+    pub report_name: String,
+    pub kind: ReportKind,
+    pub org_id: Uuid,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub format: ReportFormat,
+    pub delivery: String,
+    pub status: ReportRunStatus,
+    pub error: Option<String>,
+    pub content: Option<String>,
+}
+
+/// Filters for listing report runs.
+#[derive(Debug, Clone, Default)]
+pub struct ReportRunFilter {
+    pub org_id: Option<Uuid>,
+    pub report_name: Option<String>,
+}
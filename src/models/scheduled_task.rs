@@ -0,0 +1,223 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Where a scheduled task's output goes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScheduledTaskDelivery {
+    /// Append the prompt and the model's answer to a conversation of the
+    /// organization
+    Conversation { conversation_id: Uuid },
+    /// POST the run to a URL
+    Webhook {
+        url: String,
+        /// HMAC secret used to sign the body in the `X-Hadrian-Signature`
+        /// header. Write-only.
+        #[serde(default, skip_serializing)]
+        signing_secret: Option<String>,
+    },
+}
+
+impl ScheduledTaskDelivery {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScheduledTaskDelivery::Conversation { .. } => "conversation",
+            ScheduledTaskDelivery::Webhook { .. } => "webhook",
+        }
+    }
+}
+
+/// A prompt run on a recurring schedule for an organization.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ScheduledTask {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    /// Name of the task (unique per organization)
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Five-field cron expression, evaluated in UTC
+    pub schedule: String,
+    /// Model the prompt is sent to, as a client would request it
+    pub model: String,
+    /// System prompt
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+    /// Prompt sent on every run
+    pub prompt: String,
+    pub delivery: ScheduledTaskDelivery,
+    /// Disabled tasks don't run. Tasks are disabled automatically after
+    /// `[features.scheduled_tasks] max_consecutive_failures` failed runs.
+    pub enabled: bool,
+    /// When the task next runs; unset while disabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_run_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_run_at: Option<DateTime<Utc>>,
+    /// Failed runs since the last successful one
+    pub consecutive_failures: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request to create a scheduled task
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CreateScheduledTask {
+    /// Name of the task (unique per organization)
+    #[validate(length(min = 1, max = 255))]
+    pub name: String,
+    #[validate(length(max = 1000))]
+    pub description: Option<String>,
+    /// Five-field cron expression (`minute hour day-of-month month
+    /// day-of-week`), evaluated in UTC, e.g. `0 8 * * 1-5`
+    #[validate(length(min = 1, max = 255))]
+    pub schedule: String,
+    /// Model the prompt is sent to, e.g. `openai/gpt-4o-mini`
+    #[validate(length(min = 1, max = 255))]
+    pub model: String,
+    /// System prompt
+    pub instructions: Option<String>,
+    /// Prompt sent on every run
+    #[validate(length(min = 1, max = 32768))]
+    pub prompt: String,
+    pub delivery: ScheduledTaskDelivery,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Request to update a scheduled task. Omitted fields are left unchanged.
+#[derive(Debug, Clone, Default, Deserialize, Validate)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct UpdateScheduledTask {
+    #[validate(length(min = 1, max = 255))]
+    pub name: Option<String>,
+    #[validate(length(max = 1000))]
+    pub description: Option<String>,
+    #[validate(length(min = 1, max = 255))]
+    pub schedule: Option<String>,
+    #[validate(length(min = 1, max = 255))]
+    pub model: Option<String>,
+    pub instructions: Option<String>,
+    #[validate(length(min = 1, max = 32768))]
+    pub prompt: Option<String>,
+    pub delivery: Option<ScheduledTaskDelivery>,
+    /// Enabling a task resets its failure count
+    pub enabled: Option<bool>,
+}
+
+impl UpdateScheduledTask {
+    /// Apply the update to `task`.
+    pub fn apply(self, task: &mut ScheduledTask) {
+        if let Some(name) = self.name {
+            task.name = name;
+        }
+        if let Some(description) = self.description {
+            task.description = Some(description);
+        }
+        if let Some(schedule) = self.schedule {
+            task.schedule = schedule;
+        }
+        if let Some(model) = self.model {
+            task.model = model;
+        }
+        if let Some(instructions) = self.instructions {
+            task.instructions = Some(instructions);
+        }
+        if let Some(prompt) = self.prompt {
+            task.prompt = prompt;
+        }
+        if let Some(delivery) = self.delivery {
+            task.delivery = delivery;
+        }
+        if let Some(enabled) = self.enabled {
+            if enabled {
+                task.consecutive_failures = 0;
+            }
+            task.enabled = enabled;
+        }
+    }
+}
+
+/// Outcome of a scheduled task run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduledTaskRunStatus {
+    /// The output was generated and delivered
+    Succeeded,
+    Failed,
+}
+
+impl ScheduledTaskRunStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScheduledTaskRunStatus::Succeeded => "succeeded",
+            ScheduledTaskRunStatus::Failed => "failed",
+        }
+    }
+}
+
+impl std::str::FromStr for ScheduledTaskRunStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "succeeded" => Ok(ScheduledTaskRunStatus::Succeeded),
+            "failed" => Ok(ScheduledTaskRunStatus::Failed),
+            _ => Err(format!("Invalid scheduled task run status: {}", s)),
+        }
+    }
+}
+
+/// One run of a scheduled task
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ScheduledTaskRun {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub status: ScheduledTaskRunStatus,
+    /// The model's answer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost_microcents: Option<i64>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+}
+
+/// A finished run, recorded together with the task's next schedule.
+#[derive(Debug, Clone)]
+pub struct CreateScheduledTaskRun {
+    /// Chosen before the run starts, so its usage records carry the run ID
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub status: ScheduledTaskRunStatus,
+    pub output_text: Option<String>,
+    pub error: Option<String>,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cost_microcents: Option<i64>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+}
+
+/// Task state written alongside a run
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScheduledTaskProgress {
+    pub next_run_at: Option<DateTime<Utc>>,
+    pub consecutive_failures: i32,
+    pub enabled: bool,
+}
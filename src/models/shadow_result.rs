@@ -0,0 +1,123 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// One request mirrored by a `[features.shadow_traffic]` rule, with the
+/// primary (client-facing) and shadow responses side by side.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ShadowResult {
+    pub id: Uuid,
+    /// Name of the shadow rule that mirrored the request.
+    pub rule: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<Uuid>,
+    pub primary_provider: String,
+    pub primary_model: String,
+    pub primary_status: i32,
+    pub primary_latency_ms: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primary_input_tokens: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primary_output_tokens: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primary_cost_microcents: Option<i64>,
+    /// Assistant output, truncated to `max_output_bytes`.
+    pub primary_output: String,
+    pub shadow_provider: String,
+    pub shadow_model: String,
+    /// HTTP status from the shadow provider. Absent when the call failed
+    /// before a response (timeout, connection error).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shadow_status: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shadow_latency_ms: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shadow_input_tokens: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shadow_output_tokens: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shadow_cost_microcents: Option<i64>,
+    /// Assistant output, truncated to `max_output_bytes`.
+    pub shadow_output: String,
+    /// Why the shadow call failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shadow_error: Option<String>,
+    /// Share of output lines common to both responses, from 0.0 to 1.0.
+    /// Absent when either call failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub similarity: Option<f64>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Input for recording a mirrored request.
+#[derive(Debug, Clone, Default)]
+pub struct CreateShadowResult {
+    pub rule: String,
+    pub org_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+    pub primary_provider: String,
+    pub primary_model: String,
+    pub primary_status: i32,
+    pub primary_latency_ms: i64,
+    pub primary_input_tokens: Option<i64>,
+    pub primary_output_tokens: Option<i64>,
+    pub primary_cost_microcents: Option<i64>,
+    pub primary_output: String,
+    pub shadow_provider: String,
+    pub shadow_model: String,
+    pub shadow_status: Option<i32>,
+    pub shadow_latency_ms: Option<i64>,
+    pub shadow_input_tokens: Option<i64>,
+    pub shadow_output_tokens: Option<i64>,
+    pub shadow_cost_microcents: Option<i64>,
+    pub shadow_output: String,
+    pub shadow_error: Option<String>,
+    pub similarity: Option<f64>,
+}
+
+/// Which shadow results to list, summarize or delete.
+#[derive(Debug, Clone, Default)]
+pub struct ShadowResultFilter {
+    pub rule: Option<String>,
+    pub org_id: Option<Uuid>,
+    /// Inclusive lower bound on `created_at`.
+    pub from: Option<DateTime<Utc>>,
+    /// Exclusive upper bound on `created_at`.
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// Aggregate comparison of the primary and shadow side of one rule and
+/// provider/model pair.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ShadowReportRow {
+    pub rule: String,
+    pub primary_provider: String,
+    pub primary_model: String,
+    pub shadow_provider: String,
+    pub shadow_model: String,
+    /// Mirrored requests.
+    pub samples: i64,
+    /// Primary responses with a non-2xx status.
+    pub primary_errors: i64,
+    /// Shadow calls that failed or returned a non-2xx status.
+    pub shadow_errors: i64,
+    /// Samples where both outputs were identical.
+    pub identical: i64,
+    /// Mean similarity over samples where both calls succeeded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_similarity: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primary_avg_latency_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shadow_avg_latency_ms: Option<f64>,
+    pub primary_output_tokens: i64,
+    pub shadow_output_tokens: i64,
+    /// Total primary cost, over samples with known pricing.
+    pub primary_cost_microcents: i64,
+    /// Total shadow cost, over samples with known pricing.
+    pub shadow_cost_microcents: i64,
+}
@@ -0,0 +1,441 @@
+//! Claim-based role rules for SSO JIT provisioning.
+//!
+//! Group mappings only look at the groups claim. Role rules match on any
+//! claim from the ID token (or SAML attribute) and grant a team or project
+//! membership when every condition holds, e.g.:
+//!
+//! ```json
+//! {
+//!   "name": "ML engineers",
+//!   "conditions": [{ "claim": "department", "operator": "equals", "value": "ML" }],
+//!   "team_id": "0b0c7b8e-8d7e-4a55-9b7e-2f1f3f0b8a11",
+//!   "role": "engineer"
+//! }
+//! ```
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+/// How a claim is compared against a condition value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ClaimOperator {
+    /// Claim equals `value` (any element, for array claims)
+    Equals,
+    /// Claim is present and no element equals `value`
+    NotEquals,
+    /// Claim equals one of the values in the `value` array
+    In,
+    /// String claim contains `value` as a substring, or array claim contains `value`
+    Contains,
+    /// Claim matches the regular expression in `value`
+    Matches,
+    /// Claim is present and not null (`value` is ignored)
+    Exists,
+}
+
+/// A single condition on an identity claim.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ClaimCondition {
+    /// Claim name. Use dots to reach nested claims (e.g. `address.country`).
+    pub claim: String,
+    /// Comparison to apply
+    pub operator: ClaimOperator,
+    /// Value to compare against (an array for `in`, a pattern for `matches`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<serde_json::Value>,
+}
+
+/// A claim-to-role rule evaluated during JIT provisioning.
+///
+/// The rule matches when all of its conditions match. It grants `role` in
+/// exactly one of `team_id` or `project_id`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[validate(schema(function = "validate_rule"))]
+pub struct SsoRoleRule {
+    /// Human-readable name shown in audit logs and dry-run results
+    #[validate(length(min = 1, max = 128))]
+    pub name: String,
+    /// Conditions that must all match
+    #[validate(length(min = 1, max = 20))]
+    pub conditions: Vec<ClaimCondition>,
+    /// Team to add the user to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub team_id: Option<Uuid>,
+    /// Project to add the user to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<Uuid>,
+    /// Role to grant in the team or project
+    #[validate(length(min = 1, max = 32))]
+    pub role: String,
+    /// Higher priority wins when several rules target the same team or project
+    #[serde(default)]
+    pub priority: i32,
+}
+
+fn validate_rule(rule: &SsoRoleRule) -> Result<(), ValidationError> {
+    if rule.team_id.is_some() == rule.project_id.is_some() {
+        return Err(ValidationError::new("target")
+            .with_message("exactly one of team_id or project_id must be set".into()));
+    }
+    for condition in &rule.conditions {
+        if condition.claim.is_empty() || condition.claim.len() > 256 {
+            return Err(ValidationError::new("claim")
+                .with_message("claim names must be 1-256 characters".into()));
+        }
+        match (condition.operator, &condition.value) {
+            (ClaimOperator::Exists, _) => {}
+            (ClaimOperator::In, Some(serde_json::Value::Array(_))) => {}
+            (ClaimOperator::In, _) => {
+                return Err(ValidationError::new("value")
+                    .with_message("'in' conditions need an array value".into()));
+            }
+            (ClaimOperator::Matches, Some(serde_json::Value::String(pattern))) => {
+                if regex::Regex::new(pattern).is_err() {
+                    return Err(ValidationError::new("value")
+                        .with_message(format!("invalid regular expression: {pattern}").into()));
+                }
+            }
+            (ClaimOperator::Matches, _) => {
+                return Err(ValidationError::new("value")
+                    .with_message("'matches' conditions need a string pattern".into()));
+            }
+            (_, None | Some(serde_json::Value::Null)) => {
+                return Err(ValidationError::new("value").with_message(
+                    "conditions need a value unless the operator is 'exists'".into(),
+                ));
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// An organization's role rules, as read and replaced by the rules editor API.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct SsoRoleRules {
+    /// Rules in evaluation order
+    #[validate(length(max = 200), nested)]
+    pub rules: Vec<SsoRoleRule>,
+}
+
+/// Where a resolved role rule grants membership.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(tag = "type", content = "id", rename_all = "snake_case")]
+pub enum RoleRuleTarget {
+    Team(Uuid),
+    Project(Uuid),
+}
+
+/// A membership granted by a matching role rule.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ResolvedRoleRule {
+    /// Team or project the user is added to
+    pub target: RoleRuleTarget,
+    /// Role granted
+    pub role: String,
+    /// Name of the rule that granted it
+    pub rule: String,
+}
+
+impl SsoRoleRule {
+    pub fn target(&self) -> Option<RoleRuleTarget> {
+        match (self.team_id, self.project_id) {
+            (Some(team_id), _) => Some(RoleRuleTarget::Team(team_id)),
+            (None, Some(project_id)) => Some(RoleRuleTarget::Project(project_id)),
+            (None, None) => None,
+        }
+    }
+
+    /// Whether every condition holds for `claims`.
+    pub fn matches(&self, claims: &HashMap<String, serde_json::Value>) -> bool {
+        !self.conditions.is_empty() && self.conditions.iter().all(|c| c.matches(claims))
+    }
+}
+
+impl ClaimCondition {
+    pub fn matches(&self, claims: &HashMap<String, serde_json::Value>) -> bool {
+        let Some(claim) = lookup_claim(claims, &self.claim) else {
+            return false;
+        };
+        if claim.is_null() {
+            return false;
+        }
+        // Array claims (groups, roles, multi-valued SAML attributes) match
+        // when any element does.
+        let values: Vec<&serde_json::Value> = match claim {
+            serde_json::Value::Array(items) => items.iter().collect(),
+            other => vec![other],
+        };
+
+        match self.operator {
+            ClaimOperator::Exists => true,
+            ClaimOperator::Equals => self
+                .value
+                .as_ref()
+                .is_some_and(|expected| values.iter().any(|v| values_equal(v, expected))),
+            ClaimOperator::NotEquals => self
+                .value
+                .as_ref()
+                .is_some_and(|expected| !values.iter().any(|v| values_equal(v, expected))),
+            ClaimOperator::In => match &self.value {
+                Some(serde_json::Value::Array(allowed)) => values
+                    .iter()
+                    .any(|v| allowed.iter().any(|a| values_equal(v, a))),
+                _ => false,
+            },
+            ClaimOperator::Contains => match (&self.value, claim) {
+                (Some(expected), serde_json::Value::Array(items)) => {
+                    items.iter().any(|v| values_equal(v, expected))
+                }
+                (Some(serde_json::Value::String(needle)), serde_json::Value::String(s)) => {
+                    s.contains(needle.as_str())
+                }
+                _ => false,
+            },
+            ClaimOperator::Matches => {
+                let Some(serde_json::Value::String(pattern)) = &self.value else {
+                    return false;
+                };
+                let Ok(re) = regex::Regex::new(pattern) else {
+                    return false;
+                };
+                values
+                    .iter()
+                    .any(|v| v.as_str().is_some_and(|s| re.is_match(s)))
+            }
+        }
+    }
+}
+
+/// Look up a claim by name, falling back to a dotted path into nested objects.
+fn lookup_claim<'a>(
+    claims: &'a HashMap<String, serde_json::Value>,
+    name: &str,
+) -> Option<&'a serde_json::Value> {
+    if let Some(value) = claims.get(name) {
+        return Some(value);
+    }
+    let mut parts = name.split('.');
+    let mut current = claims.get(parts.next()?)?;
+    for part in parts {
+        current = current.get(part)?;
+    }
+    Some(current)
+}
+
+/// Compare a claim with a configured value. IdPs are inconsistent about
+/// sending numbers and booleans as strings, so scalars compare by their
+/// string form.
+fn values_equal(claim: &serde_json::Value, expected: &serde_json::Value) -> bool {
+    match (claim, expected) {
+        (serde_json::Value::String(a), serde_json::Value::String(b)) => a == b,
+        (
+            serde_json::Value::String(_)
+            | serde_json::Value::Number(_)
+            | serde_json::Value::Bool(_),
+            serde_json::Value::String(_)
+            | serde_json::Value::Number(_)
+            | serde_json::Value::Bool(_),
+        ) => scalar_string(claim) == scalar_string(expected),
+        _ => claim == expected,
+    }
+}
+
+fn scalar_string(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Evaluate `rules` against `claims`.
+///
+/// Each team or project appears at most once; when several matching rules
+/// target it, the highest `priority` wins, then the earliest rule.
+pub fn resolve_role_rules(
+    rules: &[SsoRoleRule],
+    claims: &HashMap<String, serde_json::Value>,
+) -> Vec<ResolvedRoleRule> {
+    let mut matched: Vec<&SsoRoleRule> = rules.iter().filter(|r| r.matches(claims)).collect();
+    // Stable sort keeps rule order for equal priorities
+    matched.sort_by_key(|r| std::cmp::Reverse(r.priority));
+
+    let mut resolved: Vec<ResolvedRoleRule> = Vec::new();
+    for rule in matched {
+        let Some(target) = rule.target() else {
+            continue;
+        };
+        if resolved.iter().any(|r| r.target == target) {
+            continue;
+        }
+        resolved.push(ResolvedRoleRule {
+            target,
+            role: rule.role.clone(),
+            rule: rule.name.clone(),
+        });
+    }
+    resolved
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn claims(value: serde_json::Value) -> HashMap<String, serde_json::Value> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn condition(claim: &str, operator: ClaimOperator, value: serde_json::Value) -> ClaimCondition {
+        ClaimCondition {
+            claim: claim.to_string(),
+            operator,
+            value: Some(value),
+        }
+    }
+
+    fn team_rule(
+        name: &str,
+        team_id: Uuid,
+        role: &str,
+        conditions: Vec<ClaimCondition>,
+    ) -> SsoRoleRule {
+        SsoRoleRule {
+            name: name.to_string(),
+            conditions,
+            team_id: Some(team_id),
+            project_id: None,
+            role: role.to_string(),
+            priority: 0,
+        }
+    }
+
+    #[test]
+    fn test_condition_operators() {
+        let c = claims(json!({
+            "department": "ML",
+            "level": 5,
+            "groups": ["eng", "ml-research"],
+            "address": { "country": "NZ" },
+            "email": "ada@example.com"
+        }));
+
+        assert!(condition("department", ClaimOperator::Equals, json!("ML")).matches(&c));
+        assert!(!condition("department", ClaimOperator::Equals, json!("ml")).matches(&c));
+        assert!(condition("level", ClaimOperator::Equals, json!("5")).matches(&c));
+        assert!(condition("groups", ClaimOperator::Equals, json!("eng")).matches(&c));
+        assert!(condition("department", ClaimOperator::NotEquals, json!("Sales")).matches(&c));
+        assert!(!condition("missing", ClaimOperator::NotEquals, json!("Sales")).matches(&c));
+        assert!(condition("department", ClaimOperator::In, json!(["ML", "AI"])).matches(&c));
+        assert!(condition("groups", ClaimOperator::Contains, json!("ml-research")).matches(&c));
+        assert!(condition("email", ClaimOperator::Contains, json!("@example.com")).matches(&c));
+        assert!(
+            condition(
+                "email",
+                ClaimOperator::Matches,
+                json!("^[a-z]+@example\\.com$")
+            )
+            .matches(&c)
+        );
+        assert!(condition("address.country", ClaimOperator::Equals, json!("NZ")).matches(&c));
+
+        let exists = ClaimCondition {
+            claim: "department".to_string(),
+            operator: ClaimOperator::Exists,
+            value: None,
+        };
+        assert!(exists.matches(&c));
+        assert!(!exists.matches(&claims(json!({ "department": null }))));
+    }
+
+    #[test]
+    fn test_resolve_requires_all_conditions() {
+        let team = Uuid::new_v4();
+        let rules = vec![team_rule(
+            "ml-engineers",
+            team,
+            "engineer",
+            vec![
+                condition("department", ClaimOperator::Equals, json!("ML")),
+                condition("employment", ClaimOperator::Equals, json!("full_time")),
+            ],
+        )];
+
+        let resolved = resolve_role_rules(
+            &rules,
+            &claims(json!({ "department": "ML", "employment": "full_time" })),
+        );
+        assert_eq!(
+            resolved,
+            vec![ResolvedRoleRule {
+                target: RoleRuleTarget::Team(team),
+                role: "engineer".to_string(),
+                rule: "ml-engineers".to_string(),
+            }]
+        );
+
+        assert!(resolve_role_rules(&rules, &claims(json!({ "department": "ML" }))).is_empty());
+    }
+
+    #[test]
+    fn test_resolve_highest_priority_wins_per_target() {
+        let team = Uuid::new_v4();
+        let project = Uuid::new_v4();
+        let ml = condition("department", ClaimOperator::Equals, json!("ML"));
+        let mut lead = team_rule("leads", team, "admin", vec![ml.clone()]);
+        lead.priority = 10;
+        let rules = vec![
+            team_rule("everyone", team, "member", vec![ml.clone()]),
+            lead,
+            SsoRoleRule {
+                name: "ml-project".to_string(),
+                conditions: vec![ml],
+                team_id: None,
+                project_id: Some(project),
+                role: "member".to_string(),
+                priority: 0,
+            },
+        ];
+
+        let resolved = resolve_role_rules(&rules, &claims(json!({ "department": "ML" })));
+        assert_eq!(resolved.len(), 2);
+        assert_eq!(resolved[0].target, RoleRuleTarget::Team(team));
+        assert_eq!(resolved[0].role, "admin");
+        assert_eq!(resolved[1].target, RoleRuleTarget::Project(project));
+    }
+
+    #[test]
+    fn test_rule_validation() {
+        let mut rule = team_rule(
+            "r",
+            Uuid::new_v4(),
+            "member",
+            vec![condition("department", ClaimOperator::Equals, json!("ML"))],
+        );
+        assert!(rule.validate().is_ok());
+
+        rule.project_id = Some(Uuid::new_v4());
+        assert!(rule.validate().is_err());
+        rule.project_id = None;
+
+        rule.conditions = vec![condition("department", ClaimOperator::In, json!("ML"))];
+        assert!(rule.validate().is_err());
+
+        rule.conditions = vec![condition("email", ClaimOperator::Matches, json!("("))];
+        assert!(rule.validate().is_err());
+
+        rule.conditions = vec![];
+        assert!(rule.validate().is_err());
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -57,6 +59,34 @@ pub struct UsageLogRecord {
     /// drove the tool); a shell can exit `0` while the wrapping request
     /// returns 200, or exit `7` while the request still returns 200.
     pub tool_exit_code: Option<i32>,
+    /// `sub` claim of the externally-issued JWT that authenticated the request
+    pub jwt_subject: Option<String>,
+    /// Error category (`rate_limited`, `context_length`, ...) for failed requests
+    pub error_code: Option<String>,
+    /// Repairs made to a `json_schema` response; `None` when not validated
+    pub structured_output_repairs: Option<i32>,
+    /// Estimated prompt tokens as sent, when context compression ran
+    pub context_original_tokens: Option<i32>,
+    /// Estimated prompt tokens after context compression
+    pub context_compressed_tokens: Option<i32>,
+    /// Model the client requested, when the request was downgraded
+    pub degraded_from_model: Option<String>,
+    /// Why the request was downgraded
+    pub degradation_reason: Option<String>,
+    /// Time to first token in milliseconds — only for streamed requests
+    pub ttft_ms: Option<i32>,
+    /// Mean gap between streamed chunks in milliseconds
+    pub inter_token_latency_ms: Option<f64>,
+    /// Output tokens per second between the first and last chunk
+    pub output_tokens_per_second: Option<f64>,
+    /// `Idempotency-Key` the client sent with the request
+    pub idempotency_key: Option<String>,
+    /// Attempts sent to the provider, counting retries
+    pub provider_attempts: Option<i32>,
+    /// Earlier attempts that timed out and may still have been billed upstream
+    pub suspected_duplicate_attempts: Option<i32>,
+    /// Cost allocation tags the client sent in `X-Hadrian-Tags`
+    pub tags: Option<BTreeMap<String, String>>,
 }
 
 /// Usage log entry for a single API request.
@@ -150,6 +180,61 @@ pub struct UsageLogEntry {
     /// the "never reported" vs "exited 0" distinction.
     #[serde(default)]
     pub tool_exit_code: Option<i32>,
+    /// Subject (`sub` claim) when authenticated with an externally-issued JWT
+    /// via `[[auth.jwt]]`, for attributing usage to callers without a user record
+    #[serde(default)]
+    pub jwt_subject: Option<String>,
+    /// Error category from the gateway's error taxonomy when the request
+    /// failed (see `providers::error::ErrorCategory`)
+    #[serde(default)]
+    pub error_code: Option<String>,
+    /// Re-prompts made because a `json_schema` response failed validation
+    /// (see `[features.structured_outputs]`); `None` when not validated
+    #[serde(default)]
+    pub structured_output_repairs: Option<i32>,
+    /// Estimated prompt tokens as the client sent them, when context
+    /// compression shortened the conversation (see
+    /// `[features.context_compression]`)
+    #[serde(default)]
+    pub context_original_tokens: Option<i32>,
+    /// Estimated prompt tokens after context compression
+    #[serde(default)]
+    pub context_compressed_tokens: Option<i32>,
+    /// Model the client requested, when the request was downgraded along
+    /// its organization's degradation ladder
+    #[serde(default)]
+    pub degraded_from_model: Option<String>,
+    /// `rate_limited`, `over_budget` or `circuit_open`
+    #[serde(default)]
+    pub degradation_reason: Option<String>,
+    /// Time from sending the request to receiving the first streamed chunk,
+    /// in milliseconds; `None` for non-streaming requests
+    #[serde(default)]
+    pub ttft_ms: Option<i32>,
+    /// Mean gap between consecutive streamed chunks in milliseconds; `None`
+    /// when fewer than two chunks arrived
+    #[serde(default)]
+    pub inter_token_latency_ms: Option<f64>,
+    /// Output tokens divided by the time between the first and last chunk
+    #[serde(default)]
+    pub output_tokens_per_second: Option<f64>,
+    /// `Idempotency-Key` the client sent with the request, so retried
+    /// submissions can be traced back to one charge
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
+    /// Attempts sent to the provider, counting retries. `None` when the
+    /// request didn't reach a provider through the retry loop.
+    #[serde(default)]
+    pub provider_attempts: Option<i32>,
+    /// Earlier attempts that timed out before being retried. The provider
+    /// may have finished and billed them, so each is a suspected duplicate
+    /// upstream charge.
+    #[serde(default)]
+    pub suspected_duplicate_attempts: Option<i32>,
+    /// Cost allocation tags from the `X-Hadrian-Tags` header, e.g.
+    /// `{"feature": "autocomplete"}`
+    #[serde(default)]
+    pub tags: Option<BTreeMap<String, String>>,
 }
 
 fn default_record_type() -> String {
@@ -420,6 +505,113 @@ pub struct DailyOrgSpend {
     pub character_count: i64,
 }
 
+/// Usage grouped by the value of one cost allocation tag
+#[derive(Debug, Clone, Serialize)]
+pub struct TagSpend {
+    /// Tag value; `None` for requests without the tag
+    pub tag_value: Option<String>,
+    /// Total cost in microcents (1/1,000,000 of a dollar)
+    pub total_cost_microcents: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub total_tokens: i64,
+    pub request_count: i64,
+}
+
+/// Daily usage grouped by the value of one cost allocation tag
+#[derive(Debug, Clone, Serialize)]
+pub struct DailyTagSpend {
+    pub date: NaiveDate,
+    /// Tag value; `None` for requests without the tag
+    pub tag_value: Option<String>,
+    /// Total cost in microcents (1/1,000,000 of a dollar)
+    pub total_cost_microcents: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub total_tokens: i64,
+    pub request_count: i64,
+}
+
+/// Spend by API key and organization in one hour, the baseline for
+/// anomaly detection
+#[derive(Debug, Clone, Serialize)]
+pub struct HourlySpend {
+    pub api_key_id: Option<Uuid>,
+    pub org_id: Option<Uuid>,
+    /// Start of the hour
+    pub hour: DateTime<Utc>,
+    /// Total cost in microcents (1/1,000,000 of a dollar)
+    pub total_cost_microcents: i64,
+    pub request_count: i64,
+}
+
+/// Model requests by API key, organization, model and referer, used to spot
+/// shifts in model mix and traffic from new referers
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageMix {
+    pub api_key_id: Option<Uuid>,
+    pub org_id: Option<Uuid>,
+    pub model: String,
+    pub referer: Option<String>,
+    /// Total cost in microcents (1/1,000,000 of a dollar)
+    pub total_cost_microcents: i64,
+    pub request_count: i64,
+}
+
+/// Model requests per day, provider and model with the provider attempts
+/// behind them, for reconciling against provider usage reports
+#[derive(Debug, Clone, Serialize)]
+pub struct DailyProviderAttempts {
+    pub date: NaiveDate,
+    pub provider: String,
+    pub model: String,
+    pub request_count: i64,
+    /// Attempts sent to the provider, counting retries. Requests recorded
+    /// without attempt counts (e.g. cache hits) add nothing.
+    pub provider_attempts: i64,
+    /// Attempts that timed out before a retry and may have been billed
+    pub suspected_duplicate_attempts: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cost_microcents: i64,
+}
+
+/// Upper bounds in milliseconds of the latency buckets reliability queries
+/// count requests into. Percentiles are interpolated within a bucket, so
+/// they're estimates; anything slower than the last bound reports that bound.
+pub const RELIABILITY_LATENCY_BUCKETS_MS: &[i64] = &[
+    100, 250, 500, 1_000, 2_500, 5_000, 10_000, 30_000, 60_000, 120_000,
+];
+
+/// Model request outcomes and latencies per day, provider, model and
+/// project, rolled up into reliability breakdowns
+#[derive(Debug, Clone, Serialize)]
+pub struct DailyReliability {
+    pub date: NaiveDate,
+    pub provider: String,
+    pub model: String,
+    pub project_id: Option<Uuid>,
+    pub project_name: Option<String>,
+    pub request_count: i64,
+    /// Requests recorded with any error category
+    pub error_count: i64,
+    /// Upstream failures: `provider_error`, `provider_unavailable` and
+    /// `timeout`
+    pub provider_errors: i64,
+    /// Requests blocked by a guardrail or provider safety filter
+    /// (`content_filtered`)
+    pub guardrail_blocks: i64,
+    /// Requests rejected by a gateway or provider rate limit
+    pub rate_limited: i64,
+    /// Requests that read prompt tokens from the provider's cache
+    pub cache_hits: i64,
+    /// Requests with a recorded latency
+    pub latency_count: i64,
+    /// Requests with a latency at or under each bound of
+    /// [`RELIABILITY_LATENCY_BUCKETS_MS`] (cumulative)
+    pub latency_buckets: Vec<i64>,
+}
+
 /// Cost forecast for predicting remaining budget lifespan
 #[derive(Debug, Clone, Serialize)]
 pub struct CostForecast {
@@ -0,0 +1,107 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// What kind of departure from the baseline an anomaly describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum AnomalyKind {
+    /// Spend in the detection window far above the baseline hourly spend.
+    SpendSpike,
+    /// Requests spread across models very differently from the baseline.
+    ModelMix,
+    /// Traffic from a referer never seen in the baseline.
+    NewReferer,
+}
+
+impl AnomalyKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::SpendSpike => "spend_spike",
+            Self::ModelMix => "model_mix",
+            Self::NewReferer => "new_referer",
+        }
+    }
+}
+
+impl std::str::FromStr for AnomalyKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "spend_spike" => Ok(Self::SpendSpike),
+            "model_mix" => Ok(Self::ModelMix),
+            "new_referer" => Ok(Self::NewReferer),
+            _ => Err(format!("Invalid anomaly kind: {}", s)),
+        }
+    }
+}
+
+/// A usage anomaly found by `[features.anomaly_detection]` for an API key,
+/// or for a whole organization when `api_key_id` is absent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct UsageAnomaly {
+    pub id: Uuid,
+    pub kind: AnomalyKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key_id: Option<Uuid>,
+    /// Model (`model_mix`) or referer (`new_referer`) the finding is about.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Human-readable description of the finding.
+    pub summary: String,
+    /// Observed value in the window: spend in microcents (`spend_spike`),
+    /// the request share of the `detail` model (`model_mix`), or requests
+    /// (`new_referer`).
+    pub observed: f64,
+    /// Value the baseline predicts for the window, in the same unit.
+    pub expected: f64,
+    /// How far the observation departs from the baseline: a z-score for
+    /// `spend_spike`, the total variation distance between model shares for
+    /// `model_mix`, and the request count for `new_referer`.
+    pub score: f64,
+    pub window_start: DateTime<Utc>,
+    pub window_end: DateTime<Utc>,
+    /// When the temporary rate limit applied to the API key lifts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate_limited_until: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub acknowledged_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub acknowledged_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Input for recording an anomaly.
+#[derive(Debug, Clone)]
+pub struct CreateUsageAnomaly {
+    pub kind: AnomalyKind,
+    pub org_id: Option<Uuid>,
+    pub api_key_id: Option<Uuid>,
+    pub detail: Option<String>,
+    pub summary: String,
+    pub observed: f64,
+    pub expected: f64,
+    pub score: f64,
+    pub window_start: DateTime<Utc>,
+    pub window_end: DateTime<Utc>,
+    /// Identifies the finding across detection passes, so it is only
+    /// recorded once per cooldown.
+    pub fingerprint: String,
+    pub rate_limited_until: Option<DateTime<Utc>>,
+}
+
+/// Which anomalies to list.
+#[derive(Debug, Clone, Default)]
+pub struct UsageAnomalyFilter {
+    pub org_id: Option<Uuid>,
+    pub api_key_id: Option<Uuid>,
+    pub kind: Option<AnomalyKind>,
+    /// Only anomalies nobody has acknowledged yet.
+    pub unacknowledged: bool,
+}
@@ -5,6 +5,8 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use validator::{Validate, ValidationError};
 
+use super::HybridSearchOptions;
+
 /// Maximum number of key-value pairs allowed in metadata (OpenAI limit)
 const METADATA_MAX_KEYS: usize = 16;
 /// Maximum length of metadata keys (OpenAI limit)
@@ -655,15 +657,33 @@ pub struct FileCounts {
 }
 
 /// Expiration policy for collections
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub struct ExpiresAfter {
     /// Anchor timestamp for expiration ("last_active_at")
+    #[validate(custom(function = "validate_expires_after_anchor"))]
     pub anchor: String,
-    /// Number of days after anchor before expiration
+    /// Number of days after anchor before expiration (1-365)
+    #[validate(range(min = 1, max = 365))]
     pub days: i32,
 }
 
+impl ExpiresAfter {
+    /// When a store governed by this policy expires, given its anchor time.
+    pub fn expires_at(&self, anchor: DateTime<Utc>) -> DateTime<Utc> {
+        anchor + chrono::Duration::days(i64::from(self.days))
+    }
+}
+
+fn validate_expires_after_anchor(anchor: &str) -> Result<(), ValidationError> {
+    if anchor == "last_active_at" {
+        return Ok(());
+    }
+    let mut err = ValidationError::new("invalid_anchor");
+    err.message = Some("expires_after.anchor must be \"last_active_at\"".into());
+    Err(err)
+}
+
 /// Error codes for file processing failures (OpenAI-compatible)
 ///
 /// These codes indicate why a file failed to be added to a vector store.
@@ -808,6 +828,9 @@ pub struct VectorStore {
     pub metadata: Option<HashMap<String, serde_json::Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub expires_after: Option<ExpiresAfter>,
+    /// **Hadrian Extension:** Default fusion weights for hybrid search
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hybrid_search: Option<HybridSearchOptions>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub expires_at: Option<DateTime<Utc>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -989,7 +1012,13 @@ pub struct CreateVectorStore {
     #[validate(custom(function = "validate_metadata"))]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
     /// Expiration policy
+    #[validate(nested)]
     pub expires_after: Option<ExpiresAfter>,
+    /// **Hadrian Extension:** Default fusion weights for hybrid search. Used when a search
+    /// doesn't pass its own `ranking_options.hybrid_search`.
+    #[serde(default)]
+    #[validate(nested)]
+    pub hybrid_search: Option<HybridSearchOptions>,
     /// The chunking strategy used to chunk the file(s). If not set, will use the `auto` strategy.
     /// Only applicable if `file_ids` is non-empty.
     pub chunking_strategy: Option<ChunkingStrategy>,
@@ -1014,7 +1043,12 @@ pub struct UpdateVectorStore {
     #[validate(custom(function = "validate_metadata"))]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
     /// New expiration policy
+    #[validate(nested)]
     pub expires_after: Option<ExpiresAfter>,
+    /// **Hadrian Extension:** New default fusion weights for hybrid search
+    #[serde(default)]
+    #[validate(nested)]
+    pub hybrid_search: Option<HybridSearchOptions>,
 }
 
 /// Request to add a file to a vector store (create a vector store file)
@@ -0,0 +1,103 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// State of a vector store sync run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum VectorStoreSyncStatus {
+    /// The source is being listed and documents ingested.
+    Running,
+    /// Every changed document was ingested.
+    Succeeded,
+    /// Some documents failed to ingest; the rest were applied.
+    PartiallySucceeded,
+    /// The source couldn't be listed or the vector store is unusable; nothing
+    /// was applied.
+    Failed,
+}
+
+impl VectorStoreSyncStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Running => "running",
+            Self::Succeeded => "succeeded",
+            Self::PartiallySucceeded => "partially_succeeded",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+impl std::str::FromStr for VectorStoreSyncStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "running" => Ok(Self::Running),
+            "succeeded" => Ok(Self::Succeeded),
+            "partially_succeeded" => Ok(Self::PartiallySucceeded),
+            "failed" => Ok(Self::Failed),
+            _ => Err(format!("Invalid vector store sync status: {}", s)),
+        }
+    }
+}
+
+/// One pass of a `[features.vector_store_sync]` source over its vector store.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct VectorStoreSync {
+    pub id: Uuid,
+    pub vector_store_id: Uuid,
+    /// Name of the source in `[features.vector_store_sync]`.
+    pub source_name: String,
+    /// Connector type (`s3`, `git` or `confluence`).
+    pub connector: String,
+    pub status: VectorStoreSyncStatus,
+    /// Documents seen for the first time.
+    pub files_added: i64,
+    /// Documents whose version changed and were re-ingested.
+    pub files_updated: i64,
+    /// Documents that disappeared from the source and were removed.
+    pub files_deleted: i64,
+    /// Documents skipped because their version didn't change.
+    pub files_unchanged: i64,
+    /// Documents that couldn't be fetched or ingested.
+    pub files_failed: i64,
+    /// Why the run failed, or the last per-document error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub started_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// Input for starting a sync run.
+#[derive(Debug, Clone)]
+pub struct CreateVectorStoreSync {
+    pub vector_store_id: Uuid,
+    pub source_name: String,
+    pub connector: String,
+}
+
+/// Outcome recorded when a sync run finishes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VectorStoreSyncCounts {
+    pub files_added: i64,
+    pub files_updated: i64,
+    pub files_deleted: i64,
+    pub files_unchanged: i64,
+    pub files_failed: i64,
+}
+
+/// A source document that has been ingested, with the version it was
+/// ingested at. Used to detect changes on the next run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VectorStoreSyncItem {
+    /// Document key within the source (object key, repository path, page ID).
+    pub source_key: String,
+    /// Connector-specific version (ETag, blob hash, page version).
+    pub version: String,
+    /// Files API file holding the ingested content.
+    pub file_id: Uuid,
+}
@@ -0,0 +1,633 @@
+//! Grafana dashboard and Prometheus alert rule generation.
+//!
+//! The assets are built from the metric name constants in
+//! [`super::metrics::names`] and the configured providers, so what gets
+//! imported into Grafana/Prometheus always matches what this build records.
+//! Served by `GET /admin/v1/observability/grafana` and written to disk by
+//! `hadrian grafana`.
+
+use serde_json::{Value, json};
+
+use super::{
+    metrics::names,
+    slo::{FAST_BURN_THRESHOLD, SLOW_BURN_THRESHOLD},
+};
+use crate::config::{ProvidersConfig, SloConfig};
+
+/// Stable dashboard UID so re-importing replaces the existing dashboard.
+pub const DASHBOARD_UID: &str = "hadrian-gateway";
+
+/// Error ratio above which the gateway and provider error alerts fire.
+const ERROR_RATIO_THRESHOLD: f64 = 0.05;
+
+/// p95 latency in seconds above which the gateway latency alert fires.
+const LATENCY_P95_THRESHOLD_SECS: f64 = 2.0;
+
+/// Configured provider names in a stable order.
+fn provider_names(providers: &ProvidersConfig) -> Vec<&str> {
+    let mut names: Vec<&str> = providers.iter().map(|(name, _)| name).collect();
+    names.sort_unstable();
+    names
+}
+
+/// Escape a provider name for use inside a PromQL label matcher.
+fn promql_str(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Incrementally lays out panels on Grafana's 24-column grid.
+struct PanelBuilder {
+    panels: Vec<Value>,
+    next_id: u32,
+    x: u32,
+    y: u32,
+    row_height: u32,
+}
+
+impl PanelBuilder {
+    fn new() -> Self {
+        Self {
+            panels: Vec::new(),
+            next_id: 1,
+            x: 0,
+            y: 0,
+            row_height: 0,
+        }
+    }
+
+    fn row(&mut self, title: &str) {
+        self.newline();
+        self.push_at(
+            json!({ "type": "row", "title": title, "collapsed": false, "panels": [] }),
+            24,
+            1,
+        );
+        self.newline();
+    }
+
+    fn stat(&mut self, title: &str, unit: &str, expr: &str) {
+        self.push(
+            json!({
+                "type": "stat",
+                "title": title,
+                "fieldConfig": { "defaults": { "unit": unit }, "overrides": [] },
+                "options": { "reduceOptions": { "calcs": ["lastNotNull"] }, "colorMode": "value" },
+                "targets": [target(expr, "", 'A')],
+            }),
+            6,
+            4,
+        );
+    }
+
+    fn timeseries(&mut self, title: &str, unit: &str, queries: &[(&str, &str)]) {
+        let targets: Vec<Value> = queries
+            .iter()
+            .zip('A'..)
+            .map(|((expr, legend), ref_id)| target(expr, legend, ref_id))
+            .collect();
+        self.push(
+            json!({
+                "type": "timeseries",
+                "title": title,
+                "fieldConfig": { "defaults": { "unit": unit }, "overrides": [] },
+                "options": { "legend": { "displayMode": "list", "placement": "bottom" } },
+                "targets": targets,
+            }),
+            12,
+            8,
+        );
+    }
+
+    fn push(&mut self, panel: Value, w: u32, h: u32) {
+        if self.x + w > 24 {
+            self.newline();
+        }
+        self.push_at(panel, w, h);
+    }
+
+    fn push_at(&mut self, mut panel: Value, w: u32, h: u32) {
+        panel["id"] = json!(self.next_id);
+        panel["gridPos"] = json!({ "x": self.x, "y": self.y, "w": w, "h": h });
+        if panel["type"] != "row" {
+            panel["datasource"] = json!({ "type": "prometheus", "uid": "${datasource}" });
+        }
+        self.panels.push(panel);
+        self.next_id += 1;
+        self.x += w;
+        self.row_height = self.row_height.max(h);
+    }
+
+    fn newline(&mut self) {
+        if self.x > 0 {
+            self.y += self.row_height;
+            self.x = 0;
+            self.row_height = 0;
+        }
+    }
+}
+
+fn target(expr: &str, legend: &str, ref_id: char) -> Value {
+    json!({
+        "datasource": { "type": "prometheus", "uid": "${datasource}" },
+        "expr": expr,
+        "legendFormat": legend,
+        "refId": ref_id.to_string(),
+    })
+}
+
+/// Build a Grafana dashboard (JSON model) for the gateway.
+///
+/// The `provider` template variable lists the configured providers; its
+/// "All" option matches any provider, so dynamic providers are still shown.
+pub fn dashboard(providers: &ProvidersConfig) -> Value {
+    let p = r#"provider=~"$provider""#;
+    let mut b = PanelBuilder::new();
+
+    b.row("Overview");
+    b.stat(
+        "Request rate",
+        "reqps",
+        &format!(
+            "sum(rate({}[$__rate_interval]))",
+            names::HTTP_REQUESTS_TOTAL
+        ),
+    );
+    b.stat(
+        "Error ratio (5xx)",
+        "percentunit",
+        &format!(
+            r#"sum(rate({0}{{status_class="5xx"}}[$__rate_interval])) / sum(rate({0}[$__rate_interval]))"#,
+            names::HTTP_REQUESTS_TOTAL
+        ),
+    );
+    b.stat(
+        "Spend (time range)",
+        "currencyUSD",
+        &format!(
+            "sum(increase({}{{{p}}}[$__range])) / 1e8",
+            names::LLM_COST_MICROCENTS_TOTAL
+        ),
+    );
+    b.stat(
+        "Active connections",
+        "short",
+        &format!("sum({})", names::ACTIVE_CONNECTIONS),
+    );
+
+    b.row("HTTP");
+    b.timeseries(
+        "Requests by status class",
+        "reqps",
+        &[(
+            &format!(
+                "sum by (status_class) (rate({}[$__rate_interval]))",
+                names::HTTP_REQUESTS_TOTAL
+            ),
+            "{{status_class}}",
+        )],
+    );
+    let http_quantile = |q: f64| {
+        format!(
+            "histogram_quantile({q}, sum by (le) (rate({}_bucket[$__rate_interval])))",
+            names::HTTP_REQUEST_DURATION_SECONDS
+        )
+    };
+    b.timeseries(
+        "Request latency",
+        "s",
+        &[
+            (&http_quantile(0.5), "p50"),
+            (&http_quantile(0.95), "p95"),
+            (&http_quantile(0.99), "p99"),
+        ],
+    );
+
+    b.row("LLM providers");
+    b.timeseries(
+        "LLM requests",
+        "reqps",
+        &[(
+            &format!(
+                "sum by (provider, status) (rate({}{{{p}}}[$__rate_interval]))",
+                names::LLM_REQUESTS_TOTAL
+            ),
+            "{{provider}} {{status}}",
+        )],
+    );
+    b.timeseries(
+        "LLM error ratio",
+        "percentunit",
+        &[(
+            &format!(
+                r#"sum by (provider) (rate({0}{{{p},status="error"}}[$__rate_interval])) / sum by (provider) (rate({0}{{{p}}}[$__rate_interval]))"#,
+                names::LLM_REQUESTS_TOTAL
+            ),
+            "{{provider}}",
+        )],
+    );
+    b.timeseries(
+        "LLM latency p95",
+        "s",
+        &[(
+            &format!(
+                "histogram_quantile(0.95, sum by (le, provider) (rate({}_bucket{{{p}}}[$__rate_interval])))",
+                names::LLM_REQUEST_DURATION_SECONDS
+            ),
+            "{{provider}}",
+        )],
+    );
+    b.timeseries(
+        "Time to first chunk p95",
+        "s",
+        &[(
+            &format!(
+                "histogram_quantile(0.95, sum by (le, provider) (rate({}_bucket{{{p}}}[$__rate_interval])))",
+                names::LLM_STREAMING_TIME_TO_FIRST_CHUNK_SECONDS
+            ),
+            "{{provider}}",
+        )],
+    );
+    b.timeseries(
+        "Streaming throughput p50",
+        "short",
+        &[(
+            &format!(
+                "histogram_quantile(0.50, sum by (le, provider) (rate({}_bucket{{{p}}}[$__rate_interval])))",
+                names::LLM_STREAMING_OUTPUT_TOKENS_PER_SECOND
+            ),
+            "{{provider}}",
+        )],
+    );
+    b.timeseries(
+        "Tokens",
+        "short",
+        &[
+            (
+                &format!(
+                    "sum by (provider) (rate({}{{{p}}}[$__rate_interval]))",
+                    names::LLM_INPUT_TOKENS_TOTAL
+                ),
+                "{{provider}} input",
+            ),
+            (
+                &format!(
+                    "sum by (provider) (rate({}{{{p}}}[$__rate_interval]))",
+                    names::LLM_OUTPUT_TOKENS_TOTAL
+                ),
+                "{{provider}} output",
+            ),
+        ],
+    );
+    b.timeseries(
+        "Spend per hour",
+        "currencyUSD",
+        &[(
+            &format!(
+                "sum by (provider) (rate({}{{{p}}}[$__rate_interval])) * 3600 / 1e8",
+                names::LLM_COST_MICROCENTS_TOTAL
+            ),
+            "{{provider}}",
+        )],
+    );
+    b.timeseries(
+        "Provider health (1 = healthy)",
+        "short",
+        &[(
+            &format!("max by (provider) ({}{{{p}}})", names::PROVIDER_HEALTH),
+            "{{provider}}",
+        )],
+    );
+    b.timeseries(
+        "Circuit breaker state (0 closed, 1 open, 2 half-open)",
+        "short",
+        &[(
+            &format!(
+                "max by (provider) ({}{{{p}}})",
+                names::PROVIDER_CIRCUIT_BREAKER_STATE
+            ),
+            "{{provider}}",
+        )],
+    );
+
+    b.row("Limits and auth");
+    b.timeseries(
+        "Budget checks",
+        "ops",
+        &[(
+            &format!(
+                "sum by (result) (rate({}[$__rate_interval]))",
+                names::BUDGET_CHECKS_TOTAL
+            ),
+            "{{result}}",
+        )],
+    );
+    b.timeseries(
+        "Rate limit checks",
+        "ops",
+        &[(
+            &format!(
+                "sum by (result) (rate({}[$__rate_interval]))",
+                names::RATE_LIMIT_CHECKS_TOTAL
+            ),
+            "{{result}}",
+        )],
+    );
+    b.timeseries(
+        "Authentication attempts",
+        "ops",
+        &[(
+            &format!(
+                "sum by (method, status) (rate({}[$__rate_interval]))",
+                names::AUTH_ATTEMPTS_TOTAL
+            ),
+            "{{method}} {{status}}",
+        )],
+    );
+
+    let provider_list = provider_names(providers);
+    let options: Vec<Value> = provider_list
+        .iter()
+        .map(|name| json!({ "text": name, "value": name, "selected": false }))
+        .collect();
+
+    json!({
+        "uid": DASHBOARD_UID,
+        "title": "Hadrian Gateway",
+        "description": format!("Generated by Hadrian {}", env!("CARGO_PKG_VERSION")),
+        "tags": ["hadrian"],
+        "editable": true,
+        "schemaVersion": 39,
+        "version": 1,
+        "refresh": "30s",
+        "time": { "from": "now-6h", "to": "now" },
+        "timezone": "browser",
+        "templating": {
+            "list": [
+                {
+                    "name": "datasource",
+                    "label": "Data source",
+                    "type": "datasource",
+                    "query": "prometheus",
+                },
+                {
+                    "name": "provider",
+                    "label": "Provider",
+                    "type": "custom",
+                    "query": provider_list.join(","),
+                    "options": options,
+                    "multi": true,
+                    "includeAll": true,
+                    "allValue": ".*",
+                    "current": { "text": "All", "value": "$__all" },
+                },
+            ]
+        },
+        "panels": b.panels,
+    })
+}
+
+/// Build Prometheus alerting rules for the gateway.
+///
+/// Gateway-wide rules are always included. Each configured provider gets an
+/// error ratio rule, plus health and circuit breaker rules when those are
+/// enabled for it (the underlying gauges are never set otherwise). Each SLO
+/// gets fast and slow burn rate rules.
+///
+/// Returned as JSON, which Prometheus accepts as a YAML rule file.
+pub fn alert_rules(providers: &ProvidersConfig, slos: &[SloConfig]) -> Value {
+    let gateway_rules = vec![
+        json!({
+            "alert": "HadrianHighErrorRate",
+            "expr": format!(
+                r#"sum(rate({0}{{status_class="5xx"}}[5m])) / sum(rate({0}[5m])) > {ERROR_RATIO_THRESHOLD}"#,
+                names::HTTP_REQUESTS_TOTAL
+            ),
+            "for": "5m",
+            "labels": { "severity": "warning" },
+            "annotations": {
+                "summary": "Hadrian gateway 5xx ratio is high",
+                "description": "More than 5% of requests failed with a 5xx status over the last 5 minutes.",
+            },
+        }),
+        json!({
+            "alert": "HadrianHighLatency",
+            "expr": format!(
+                "histogram_quantile(0.95, sum by (le) (rate({}_bucket[5m]))) > {LATENCY_P95_THRESHOLD_SECS}",
+                names::HTTP_REQUEST_DURATION_SECONDS
+            ),
+            "for": "5m",
+            "labels": { "severity": "warning" },
+            "annotations": {
+                "summary": "Hadrian gateway p95 latency is high",
+                "description": "95th percentile request latency has been above 2 seconds for 5 minutes.",
+            },
+        }),
+        json!({
+            "alert": "HadrianScheduledTaskFailures",
+            "expr": format!(
+                r#"sum(increase({}{{status="failed"}}[1h])) > 0"#,
+                names::SCHEDULED_TASK_RUNS_TOTAL
+            ),
+            "labels": { "severity": "warning" },
+            "annotations": {
+                "summary": "Hadrian scheduled tasks are failing",
+                "description": "At least one scheduled task run failed in the last hour. Tasks are disabled after repeated failures.",
+            },
+        }),
+    ];
+
+    let mut provider_rules = Vec::new();
+    for name in provider_names(providers) {
+        let Some(config) = providers.get(name) else {
+            continue;
+        };
+        let label = promql_str(name);
+
+        provider_rules.push(json!({
+            "alert": "HadrianProviderErrorRate",
+            "expr": format!(
+                r#"sum(rate({0}{{provider="{label}",status="error"}}[5m])) / sum(rate({0}{{provider="{label}"}}[5m])) > {ERROR_RATIO_THRESHOLD}"#,
+                names::LLM_REQUESTS_TOTAL
+            ),
+            "for": "5m",
+            "labels": { "severity": "warning", "provider": name },
+            "annotations": {
+                "summary": format!("Provider {name} error ratio is high"),
+                "description": format!("More than 5% of requests to {name} failed over the last 5 minutes."),
+            },
+        }));
+
+        if config.health_check_config().enabled {
+            provider_rules.push(json!({
+                "alert": "HadrianProviderUnhealthy",
+                "expr": format!(r#"max({}{{provider="{label}"}}) == 0"#, names::PROVIDER_HEALTH),
+                "for": "5m",
+                "labels": { "severity": "critical", "provider": name },
+                "annotations": {
+                    "summary": format!("Provider {name} is failing health checks"),
+                },
+            }));
+        }
+
+        if config.circuit_breaker_config().enabled {
+            provider_rules.push(json!({
+                "alert": "HadrianProviderCircuitOpen",
+                "expr": format!(
+                    r#"max({}{{provider="{label}"}}) == 1"#,
+                    names::PROVIDER_CIRCUIT_BREAKER_STATE
+                ),
+                "for": "1m",
+                "labels": { "severity": "warning", "provider": name },
+                "annotations": {
+                    "summary": format!("Circuit breaker for provider {name} is open"),
+                },
+            }));
+        }
+    }
+
+    let mut slo_rules = Vec::new();
+    for slo in slos {
+        let label = promql_str(&slo.name);
+        // Bad fraction over a window, compared to a multiple of the error budget
+        let burn = |window: &str, threshold: f64| {
+            format!(
+                r#"sum(rate({0}{{slo="{label}",result="bad"}}[{window}])) / sum(rate({0}{{slo="{label}"}}[{window}])) > {1}"#,
+                names::SLO_EVENTS_TOTAL,
+                // Round away float noise such as 0.14400000000000002
+                (threshold * (1.0 - slo.objective) * 1e9).round() / 1e9
+            )
+        };
+
+        slo_rules.push(json!({
+            "alert": "HadrianSloFastBurn",
+            "expr": format!(
+                "({}) and ({})",
+                burn("1h", FAST_BURN_THRESHOLD),
+                burn("5m", FAST_BURN_THRESHOLD)
+            ),
+            "for": "2m",
+            "labels": { "severity": "critical", "slo": slo.name },
+            "annotations": {
+                "summary": format!("SLO {} is burning its error budget fast", slo.name),
+                "description": format!(
+                    "Error budget burn rate has been above {FAST_BURN_THRESHOLD}x over the last hour and 5 minutes."
+                ),
+            },
+        }));
+        slo_rules.push(json!({
+            "alert": "HadrianSloSlowBurn",
+            "expr": format!(
+                "({}) and ({})",
+                burn("6h", SLOW_BURN_THRESHOLD),
+                burn("30m", SLOW_BURN_THRESHOLD)
+            ),
+            "for": "15m",
+            "labels": { "severity": "warning", "slo": slo.name },
+            "annotations": {
+                "summary": format!("SLO {} is burning its error budget", slo.name),
+                "description": format!(
+                    "Error budget burn rate has been above {SLOW_BURN_THRESHOLD}x over the last 6 hours and 30 minutes."
+                ),
+            },
+        }));
+    }
+
+    let mut groups = vec![json!({ "name": "hadrian-gateway", "rules": gateway_rules })];
+    if !provider_rules.is_empty() {
+        groups.push(json!({ "name": "hadrian-providers", "rules": provider_rules }));
+    }
+    if !slo_rules.is_empty() {
+        groups.push(json!({ "name": "hadrian-slos", "rules": slo_rules }));
+    }
+    json!({ "groups": groups })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn providers() -> ProvidersConfig {
+        toml::from_str(
+            r#"
+            [openai]
+            type = "open_ai"
+            api_key = "sk-test"
+            circuit_breaker = { enabled = true }
+
+            [anthropic]
+            type = "anthropic"
+            api_key = "sk-ant-test"
+        "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_dashboard_lists_configured_providers() {
+        let dashboard = dashboard(&providers());
+
+        assert_eq!(dashboard["uid"], DASHBOARD_UID);
+        let provider_var = &dashboard["templating"]["list"][1];
+        assert_eq!(provider_var["query"], "anthropic,openai");
+
+        // Panel ids are unique and every panel stays within the 24-column grid
+        let panels = dashboard["panels"].as_array().unwrap();
+        let mut ids: Vec<u64> = panels.iter().map(|p| p["id"].as_u64().unwrap()).collect();
+        ids.dedup();
+        assert_eq!(ids.len(), panels.len());
+        for panel in panels {
+            let pos = &panel["gridPos"];
+            assert!(pos["x"].as_u64().unwrap() + pos["w"].as_u64().unwrap() <= 24);
+        }
+    }
+
+    #[test]
+    fn test_alert_rules_follow_provider_config() {
+        let rules = alert_rules(&providers(), &[]);
+        let provider_rules = rules["groups"][1]["rules"].as_array().unwrap();
+        let alerts: Vec<(&str, &str)> = provider_rules
+            .iter()
+            .map(|r| {
+                (
+                    r["alert"].as_str().unwrap(),
+                    r["labels"]["provider"].as_str().unwrap(),
+                )
+            })
+            .collect();
+
+        assert!(alerts.contains(&("HadrianProviderErrorRate", "anthropic")));
+        assert!(alerts.contains(&("HadrianProviderCircuitOpen", "openai")));
+        // Circuit breaker disabled for anthropic and health checks for both
+        assert!(!alerts.contains(&("HadrianProviderCircuitOpen", "anthropic")));
+        assert!(!alerts.iter().any(|(a, _)| *a == "HadrianProviderUnhealthy"));
+    }
+
+    #[test]
+    fn test_alert_rules_without_providers() {
+        let rules = alert_rules(&ProvidersConfig::default(), &[]);
+        assert_eq!(rules["groups"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_alert_rules_for_slos() {
+        let slo: SloConfig = toml::from_str(
+            r#"
+            name = "gpt-4o-ttft"
+            indicator = "ttft"
+            threshold_ms = 800
+            objective = 0.99
+        "#,
+        )
+        .unwrap();
+        let rules = alert_rules(&ProvidersConfig::default(), &[slo]);
+
+        let slo_rules = rules["groups"][1]["rules"].as_array().unwrap();
+        assert_eq!(rules["groups"][1]["name"], "hadrian-slos");
+        assert_eq!(slo_rules[0]["alert"], "HadrianSloFastBurn");
+        assert_eq!(slo_rules[0]["labels"]["slo"], "gpt-4o-ttft");
+        let expr = slo_rules[0]["expr"].as_str().unwrap();
+        assert!(expr.contains(r#"slo_events_total{slo="gpt-4o-ttft",result="bad"}[1h]"#));
+        assert!(expr.contains("[5m])) > 0.144"));
+        assert_eq!(slo_rules[1]["alert"], "HadrianSloSlowBurn");
+    }
+}
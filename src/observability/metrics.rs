@@ -29,6 +29,27 @@ pub fn init_metrics(config: &MetricsConfig) -> Result<(), MetricsError> {
 
     // Build Prometheus exporter with custom buckets
     let builder = PrometheusBuilder::new()
+        .set_buckets_for_metric(
+            metrics_exporter_prometheus::Matcher::Full(
+                names::LLM_STREAMING_TIME_TO_FIRST_CHUNK_SECONDS.to_string(),
+            ),
+            &seconds_from_ms(&config.latency_buckets_ms),
+        )
+        .map_err(|e| MetricsError::Setup(e.to_string()))?
+        .set_buckets_for_metric(
+            metrics_exporter_prometheus::Matcher::Full(
+                names::LLM_STREAMING_INTER_TOKEN_LATENCY_SECONDS.to_string(),
+            ),
+            INTER_TOKEN_LATENCY_BUCKETS,
+        )
+        .map_err(|e| MetricsError::Setup(e.to_string()))?
+        .set_buckets_for_metric(
+            metrics_exporter_prometheus::Matcher::Full(
+                names::LLM_STREAMING_OUTPUT_TOKENS_PER_SECOND.to_string(),
+            ),
+            TOKENS_PER_SECOND_BUCKETS,
+        )
+        .map_err(|e| MetricsError::Setup(e.to_string()))?
         .set_buckets_for_metric(
             metrics_exporter_prometheus::Matcher::Suffix("_duration_seconds".to_string()),
             &seconds_from_ms(&config.latency_buckets_ms),
@@ -38,6 +59,11 @@ pub fn init_metrics(config: &MetricsConfig) -> Result<(), MetricsError> {
             metrics_exporter_prometheus::Matcher::Suffix("_tokens".to_string()),
             &config.token_buckets,
         )
+        .map_err(|e| MetricsError::Setup(e.to_string()))?
+        .set_buckets_for_metric(
+            metrics_exporter_prometheus::Matcher::Suffix("_bytes".to_string()),
+            PAYLOAD_BYTE_BUCKETS,
+        )
         .map_err(|e| MetricsError::Setup(e.to_string()))?;
 
     let handle = builder.install_recorder().map_err(MetricsError::Install)?;
@@ -56,6 +82,29 @@ pub fn init_metrics(_config: &MetricsConfig) -> Result<(), MetricsError> {
     Ok(())
 }
 
+/// Buckets for provider request/response payload sizes: 1 KiB to 64 MiB.
+#[cfg(feature = "prometheus")]
+const PAYLOAD_BYTE_BUCKETS: &[f64] = &[
+    1024.0,
+    4096.0,
+    16384.0,
+    65536.0,
+    262_144.0,
+    1_048_576.0,
+    4_194_304.0,
+    16_777_216.0,
+    67_108_864.0,
+];
+
+/// Buckets for the gap between streamed chunks: 5 ms to 2.5 s.
+#[cfg(feature = "prometheus")]
+const INTER_TOKEN_LATENCY_BUCKETS: &[f64] = &[0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5];
+
+/// Buckets for streaming output throughput in tokens per second.
+#[cfg(feature = "prometheus")]
+const TOKENS_PER_SECOND_BUCKETS: &[f64] =
+    &[5.0, 10.0, 25.0, 50.0, 100.0, 200.0, 400.0, 800.0, 1600.0];
+
 /// Convert millisecond buckets to seconds.
 #[cfg(feature = "prometheus")]
 fn seconds_from_ms(ms_buckets: &[f64]) -> Vec<f64> {
@@ -68,6 +117,36 @@ pub fn get_prometheus_handle() -> Option<&'static PrometheusHandle> {
     PROMETHEUS_HANDLE.get()
 }
 
+/// Names of the metrics referenced by the generated Grafana dashboard and
+/// Prometheus alert rules (see [`super::grafana`]). Recording sites use these
+/// constants so a rename can't silently break the provisioned assets.
+pub mod names {
+    pub const HTTP_REQUESTS_TOTAL: &str = "http_requests_total";
+    pub const HTTP_REQUEST_DURATION_SECONDS: &str = "http_request_duration_seconds";
+    pub const LLM_REQUESTS_TOTAL: &str = "llm_requests_total";
+    pub const LLM_REQUEST_DURATION_SECONDS: &str = "llm_request_duration_seconds";
+    pub const LLM_INPUT_TOKENS_TOTAL: &str = "llm_input_tokens_total";
+    pub const LLM_OUTPUT_TOKENS_TOTAL: &str = "llm_output_tokens_total";
+    pub const LLM_COST_MICROCENTS_TOTAL: &str = "llm_cost_microcents_total";
+    pub const LLM_STREAMING_TIME_TO_FIRST_CHUNK_SECONDS: &str =
+        "llm_streaming_time_to_first_chunk_seconds";
+    pub const LLM_STREAMING_INTER_TOKEN_LATENCY_SECONDS: &str =
+        "llm_streaming_inter_token_latency_seconds";
+    pub const LLM_STREAMING_OUTPUT_TOKENS_PER_SECOND: &str =
+        "llm_streaming_output_tokens_per_second";
+    pub const AUTH_ATTEMPTS_TOTAL: &str = "auth_attempts_total";
+    pub const BUDGET_CHECKS_TOTAL: &str = "budget_checks_total";
+    pub const RATE_LIMIT_CHECKS_TOTAL: &str = "rate_limit_checks_total";
+    pub const ACTIVE_CONNECTIONS: &str = "active_connections";
+    pub const PROVIDER_HEALTH: &str = "provider_health";
+    pub const PROVIDER_CIRCUIT_BREAKER_STATE: &str = "provider_circuit_breaker_state";
+    pub const SLO_EVENTS_TOTAL: &str = "slo_events_total";
+    pub const SLO_COMPLIANCE_RATIO: &str = "slo_compliance_ratio";
+    pub const SLO_ERROR_BUDGET_REMAINING: &str = "slo_error_budget_remaining";
+    pub const SLO_BURN_RATE: &str = "slo_burn_rate";
+    pub const SCHEDULED_TASK_RUNS_TOTAL: &str = "scheduled_task_runs_total";
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Metric Recording Functions
 // ─────────────────────────────────────────────────────────────────────────────
@@ -79,10 +158,10 @@ pub fn record_http_request(method: &str, path: &str, status: u16, duration_secs:
         let status_str = status.to_string();
         let status_class = format!("{}xx", status / 100);
 
-        counter!("http_requests_total", "method" => method.to_string(), "path" => path.to_string(), "status" => status_str.clone(), "status_class" => status_class.clone())
+        counter!(names::HTTP_REQUESTS_TOTAL, "method" => method.to_string(), "path" => path.to_string(), "status" => status_str.clone(), "status_class" => status_class.clone())
             .increment(1);
 
-        histogram!("http_request_duration_seconds", "method" => method.to_string(), "path" => path.to_string(), "status_class" => status_class)
+        histogram!(names::HTTP_REQUEST_DURATION_SECONDS, "method" => method.to_string(), "path" => path.to_string(), "status_class" => status_class)
             .record(duration_secs);
     }
     #[cfg(not(feature = "prometheus"))]
@@ -129,7 +208,7 @@ pub fn record_llm_request(metrics: LlmRequestMetrics<'_>) {
         // Use "0" as sentinel value instead of empty string to avoid Prometheus aggregation issues
         let status_code_str = status_code.map_or("0".to_string(), |c| c.to_string());
         counter!(
-            "llm_requests_total",
+            names::LLM_REQUESTS_TOTAL,
             "provider" => provider.to_string(),
             "model" => model.to_string(),
             "status" => status.to_string(),
@@ -137,25 +216,25 @@ pub fn record_llm_request(metrics: LlmRequestMetrics<'_>) {
         )
         .increment(1);
 
-        histogram!("llm_request_duration_seconds", "provider" => provider.to_string(), "model" => model.to_string())
+        histogram!(names::LLM_REQUEST_DURATION_SECONDS, "provider" => provider.to_string(), "model" => model.to_string())
             .record(duration_secs);
 
         if let Some(input) = input_tokens {
             histogram!("llm_input_tokens", "provider" => provider.to_string(), "model" => model.to_string())
                 .record(input as f64);
-            counter!("llm_input_tokens_total", "provider" => provider.to_string(), "model" => model.to_string())
+            counter!(names::LLM_INPUT_TOKENS_TOTAL, "provider" => provider.to_string(), "model" => model.to_string())
                 .increment(input as u64);
         }
 
         if let Some(output) = output_tokens {
             histogram!("llm_output_tokens", "provider" => provider.to_string(), "model" => model.to_string())
                 .record(output as f64);
-            counter!("llm_output_tokens_total", "provider" => provider.to_string(), "model" => model.to_string())
+            counter!(names::LLM_OUTPUT_TOKENS_TOTAL, "provider" => provider.to_string(), "model" => model.to_string())
                 .increment(output as u64);
         }
 
         if let Some(cost) = cost_microcents {
-            counter!("llm_cost_microcents_total", "provider" => provider.to_string(), "model" => model.to_string())
+            counter!(names::LLM_COST_MICROCENTS_TOTAL, "provider" => provider.to_string(), "model" => model.to_string())
                 .increment(cost as u64);
         }
     }
@@ -192,7 +271,7 @@ pub fn record_streaming_response(
 
         // Time to first chunk (TTFC) - critical latency metric
         if let Some(ttfc) = time_to_first_chunk_secs {
-            histogram!("llm_streaming_time_to_first_chunk_seconds", "provider" => provider.to_string(), "model" => model.to_string())
+            histogram!(names::LLM_STREAMING_TIME_TO_FIRST_CHUNK_SECONDS, "provider" => provider.to_string(), "model" => model.to_string())
                 .record(ttfc);
         }
 
@@ -217,12 +296,102 @@ pub fn record_streaming_response(
     }
 }
 
+/// Record the gap between two consecutive chunks of a streaming response.
+pub fn record_streaming_inter_token_latency(provider: &str, model: &str, latency_secs: f64) {
+    #[cfg(feature = "prometheus")]
+    {
+        histogram!(names::LLM_STREAMING_INTER_TOKEN_LATENCY_SECONDS, "provider" => provider.to_string(), "model" => model.to_string())
+            .record(latency_secs);
+    }
+    #[cfg(not(feature = "prometheus"))]
+    {
+        let _ = (provider, model, latency_secs);
+    }
+}
+
+/// Record the output throughput of a streaming response, measured between
+/// its first and last chunk.
+pub fn record_streaming_throughput(provider: &str, model: &str, tokens_per_second: f64) {
+    #[cfg(feature = "prometheus")]
+    {
+        histogram!(names::LLM_STREAMING_OUTPUT_TOKENS_PER_SECOND, "provider" => provider.to_string(), "model" => model.to_string())
+            .record(tokens_per_second);
+    }
+    #[cfg(not(feature = "prometheus"))]
+    {
+        let _ = (provider, model, tokens_per_second);
+    }
+}
+
+/// Record the size of a payload exchanged with a provider.
+///
+/// `direction` is `"request"` (gateway → provider) or `"response"`
+/// (provider → gateway).
+pub fn record_provider_payload_bytes(provider: &str, direction: &str, bytes: u64) {
+    #[cfg(feature = "prometheus")]
+    {
+        histogram!("llm_provider_payload_bytes", "provider" => provider.to_string(), "direction" => direction.to_string())
+            .record(bytes as f64);
+        counter!("llm_provider_payload_bytes_total", "provider" => provider.to_string(), "direction" => direction.to_string())
+            .increment(bytes);
+    }
+    #[cfg(not(feature = "prometheus"))]
+    {
+        let _ = (provider, direction, bytes);
+    }
+}
+
+/// Record the serialized size of a request payload sent to a provider.
+///
+/// Serialization only happens when metrics are compiled in; it writes into a
+/// byte counter rather than allocating a buffer.
+pub fn record_provider_request_payload<T: serde::Serialize>(provider: &str, payload: &T) {
+    #[cfg(feature = "prometheus")]
+    {
+        struct ByteCounter(u64);
+
+        impl std::io::Write for ByteCounter {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0 += buf.len() as u64;
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut counter = ByteCounter(0);
+        if serde_json::to_writer(&mut counter, payload).is_ok() {
+            record_provider_payload_bytes(provider, "request", counter.0);
+        }
+    }
+    #[cfg(not(feature = "prometheus"))]
+    {
+        let _ = (provider, payload);
+    }
+}
+
+/// Record a provider response that was aborted for exceeding the provider's
+/// `max_response_bytes` limit.
+pub fn record_provider_response_too_large(provider: &str) {
+    #[cfg(feature = "prometheus")]
+    {
+        counter!("llm_provider_response_too_large_total", "provider" => provider.to_string())
+            .increment(1);
+    }
+    #[cfg(not(feature = "prometheus"))]
+    {
+        let _ = provider;
+    }
+}
+
 /// Record authentication result.
 pub fn record_auth_attempt(method: &str, success: bool) {
     #[cfg(feature = "prometheus")]
     {
         let status = if success { "success" } else { "failure" };
-        counter!("auth_attempts_total", "method" => method.to_string(), "status" => status.to_string())
+        counter!(names::AUTH_ATTEMPTS_TOTAL, "method" => method.to_string(), "status" => status.to_string())
             .increment(1);
     }
     #[cfg(not(feature = "prometheus"))]
@@ -282,7 +451,7 @@ pub fn record_jit_provisions(resource_type: &str, outcome: &str, count: u64) {
 pub fn record_budget_check(result: &str, api_key_id: Option<uuid::Uuid>) {
     #[cfg(feature = "prometheus")]
     {
-        counter!("budget_checks_total", "result" => result.to_string()).increment(1);
+        counter!(names::BUDGET_CHECKS_TOTAL, "result" => result.to_string()).increment(1);
 
         if let Some(id) = api_key_id {
             counter!("budget_checks_by_key_total", "api_key_id" => id.to_string(), "result" => result.to_string())
@@ -331,7 +500,7 @@ pub fn record_budget_warning(api_key_id: uuid::Uuid, spend_percentage: f64, peri
 pub fn record_rate_limit(result: &str, api_key_id: Option<uuid::Uuid>) {
     #[cfg(feature = "prometheus")]
     {
-        counter!("rate_limit_checks_total", "result" => result.to_string()).increment(1);
+        counter!(names::RATE_LIMIT_CHECKS_TOTAL, "result" => result.to_string()).increment(1);
 
         if result == "limited"
             && let Some(id) = api_key_id
@@ -368,6 +537,73 @@ pub fn record_cache_operation(cache_type: &str, operation: &str, result: &str) {
     }
 }
 
+/// Record a lookup in one tier of the tiered cache.
+///
+/// `tier` is `l1` (in-process) or `l2` (Redis); L2 is only consulted after an
+/// L1 miss. `operation` is `get` or `rate_limit`.
+pub fn record_tiered_cache_lookup(tier: &str, operation: &str, result: &str) {
+    #[cfg(feature = "prometheus")]
+    {
+        counter!(
+            "cache_tier_lookups_total",
+            "tier" => tier.to_string(),
+            "operation" => operation.to_string(),
+            "result" => result.to_string()
+        )
+        .increment(1);
+    }
+    #[cfg(not(feature = "prometheus"))]
+    {
+        let _ = (tier, operation, result);
+    }
+}
+
+/// Record an embeddings cache lookup (`hit`, `miss` or `error`).
+pub fn record_embeddings_cache_lookup(result: &str) {
+    #[cfg(feature = "prometheus")]
+    counter!("hadrian_embeddings_cache_lookups_total", "result" => result.to_string()).increment(1);
+    #[cfg(not(feature = "prometheus"))]
+    let _ = result;
+}
+
+/// Record an attempt to store an embeddings response (`stored`, `too_large`
+/// or `error`).
+pub fn record_embeddings_cache_store(result: &str) {
+    #[cfg(feature = "prometheus")]
+    counter!("hadrian_embeddings_cache_stores_total", "result" => result.to_string()).increment(1);
+    #[cfg(not(feature = "prometheus"))]
+    let _ = result;
+}
+
+/// Record a Redis failure.
+///
+/// `scope` is `node` when a command failed on one node (connection dropped,
+/// node loading or demoted) and `cluster` when the deployment as a whole is
+/// unavailable (no node reachable, `CLUSTERDOWN`, `MASTERDOWN`).
+pub fn record_redis_failure(topology: &str, scope: &str) {
+    #[cfg(feature = "prometheus")]
+    counter!(
+        "cache_redis_failures_total",
+        "topology" => topology.to_string(),
+        "scope" => scope.to_string()
+    )
+    .increment(1);
+    #[cfg(not(feature = "prometheus"))]
+    let _ = (topology, scope);
+}
+
+/// Set whether the gateway currently holds a usable Redis connection.
+pub fn set_redis_connected(topology: &str, connected: bool) {
+    #[cfg(feature = "prometheus")]
+    gauge!("cache_redis_connected", "topology" => topology.to_string()).set(if connected {
+        1.0
+    } else {
+        0.0
+    });
+    #[cfg(not(feature = "prometheus"))]
+    let _ = (topology, connected);
+}
+
 /// Record dead-letter queue operation.
 pub fn record_dlq_operation(operation: &str, entry_type: &str) {
     #[cfg(feature = "prometheus")]
@@ -381,6 +617,37 @@ pub fn record_dlq_operation(operation: &str, entry_type: &str) {
     }
 }
 
+/// Record a notification email send attempt.
+///
+/// `status` is `sent`, `failed` or `rate_limited`.
+pub fn record_email_send(kind: &str, status: &str) {
+    #[cfg(feature = "prometheus")]
+    {
+        counter!("email_sends_total", "kind" => kind.to_string(), "status" => status.to_string())
+            .increment(1);
+    }
+    #[cfg(not(feature = "prometheus"))]
+    {
+        let _ = (kind, status);
+    }
+}
+
+/// Record a DLQ redrive attempt.
+///
+/// `source` is `manual`, `batch` or `policy:<name>`; `outcome` is `success`
+/// or `failure`. The success rate is the ratio of the two outcomes.
+pub fn record_dlq_redrive(source: &str, entry_type: &str, outcome: &str) {
+    #[cfg(feature = "prometheus")]
+    {
+        counter!("dlq_redrives_total", "source" => source.to_string(), "entry_type" => entry_type.to_string(), "outcome" => outcome.to_string())
+            .increment(1);
+    }
+    #[cfg(not(feature = "prometheus"))]
+    {
+        let _ = (source, entry_type, outcome);
+    }
+}
+
 /// Record data retention deletion.
 ///
 /// Tracks records deleted by the retention worker, enabling:
@@ -449,6 +716,105 @@ pub fn record_cleanup_error(job: &str) {
     }
 }
 
+/// Record a scheduled report delivery attempt.
+///
+/// # Arguments
+/// * `kind` - Report kind (e.g., "usage_summary", "key_hygiene")
+/// * `status` - Outcome of the attempt ("delivered" or "failed")
+pub fn record_scheduled_report(kind: &str, status: &str) {
+    #[cfg(feature = "prometheus")]
+    {
+        counter!(
+            "scheduled_reports_total",
+            "kind" => kind.to_string(),
+            "status" => status.to_string()
+        )
+        .increment(1);
+    }
+    #[cfg(not(feature = "prometheus"))]
+    {
+        let _ = (kind, status);
+    }
+}
+
+/// Record a scheduled task run.
+///
+/// # Arguments
+/// * `status` - Outcome of the run ("succeeded" or "failed")
+pub fn record_scheduled_task_run(status: &str) {
+    #[cfg(feature = "prometheus")]
+    {
+        counter!(names::SCHEDULED_TASK_RUNS_TOTAL, "status" => status.to_string()).increment(1);
+    }
+    #[cfg(not(feature = "prometheus"))]
+    {
+        let _ = status;
+    }
+}
+
+/// Record a usage anomaly found by anomaly detection.
+///
+/// # Arguments
+/// * `kind` - Anomaly kind ("spend_spike", "model_mix" or "new_referer")
+/// * `subject` - What the anomaly is about ("api_key" or "organization")
+/// * `rate_limited` - Whether a temporary rate limit was applied
+pub fn record_usage_anomaly(kind: &str, subject: &str, rate_limited: bool) {
+    #[cfg(feature = "prometheus")]
+    {
+        counter!(
+            "usage_anomalies_total",
+            "kind" => kind.to_string(),
+            "subject" => subject.to_string(),
+            "rate_limited" => rate_limited.to_string()
+        )
+        .increment(1);
+    }
+    #[cfg(not(feature = "prometheus"))]
+    {
+        let _ = (kind, subject, rate_limited);
+    }
+}
+
+/// Record a conversation summary generation attempt.
+///
+/// # Arguments
+/// * `status` - Outcome ("succeeded" or "failed")
+pub fn record_conversation_summary(status: &str) {
+    #[cfg(feature = "prometheus")]
+    {
+        counter!(
+            "conversation_summaries_total",
+            "status" => status.to_string()
+        )
+        .increment(1);
+    }
+    #[cfg(not(feature = "prometheus"))]
+    {
+        let _ = status;
+    }
+}
+
+/// Record a vector store sync run.
+///
+/// # Arguments
+/// * `connector` - Source connector ("s3", "git" or "confluence")
+/// * `status` - Outcome of the run ("succeeded", "partially_succeeded" or "failed")
+pub fn record_vector_store_sync(connector: &str, status: &str) {
+    #[cfg(feature = "prometheus")]
+    {
+        counter!(
+            "vector_store_syncs_total",
+            "connector" => connector.to_string(),
+            "status" => status.to_string()
+        )
+        .increment(1);
+    }
+    #[cfg(not(feature = "prometheus"))]
+    {
+        let _ = (connector, status);
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // RAG / Document Processing Metrics
 // ─────────────────────────────────────────────────────────────────────────────
@@ -813,17 +1179,33 @@ pub fn record_vector_store_operation(
 /// Update active connections gauge.
 pub fn set_active_connections(count: usize) {
     #[cfg(feature = "prometheus")]
-    gauge!("active_connections").set(count as f64);
+    gauge!(names::ACTIVE_CONNECTIONS).set(count as f64);
     #[cfg(not(feature = "prometheus"))]
     let _ = count;
 }
 
+/// Update the draining gauge (1 while the instance is draining).
+pub fn set_draining(draining: bool) {
+    #[cfg(feature = "prometheus")]
+    gauge!("gateway_draining").set(if draining { 1.0 } else { 0.0 });
+    #[cfg(not(feature = "prometheus"))]
+    let _ = draining;
+}
+
+/// Record whether this node ran the last tick of a cluster-wide job.
+pub fn set_job_leader(job: &str, leader: bool) {
+    #[cfg(feature = "prometheus")]
+    gauge!("gateway_job_leader", "job" => job.to_string()).set(if leader { 1.0 } else { 0.0 });
+    #[cfg(not(feature = "prometheus"))]
+    let _ = (job, leader);
+}
+
 /// Record provider health check.
 pub fn record_provider_health(provider: &str, healthy: bool, latency_secs: Option<f64>) {
     #[cfg(feature = "prometheus")]
     {
         let status = if healthy { "healthy" } else { "unhealthy" };
-        gauge!("provider_health", "provider" => provider.to_string()).set(if healthy {
+        gauge!(names::PROVIDER_HEALTH, "provider" => provider.to_string()).set(if healthy {
             1.0
         } else {
             0.0
@@ -860,6 +1242,22 @@ pub fn record_db_operation(operation: &str, table: &str, duration_secs: f64, suc
     }
 }
 
+/// Record whether reads are going to the Postgres read replica.
+pub fn set_db_replica_healthy(healthy: bool) {
+    #[cfg(feature = "prometheus")]
+    gauge!("db_replica_healthy").set(if healthy { 1.0 } else { 0.0 });
+    #[cfg(not(feature = "prometheus"))]
+    let _ = healthy;
+}
+
+/// Record the Postgres read replica's replication lag.
+pub fn set_db_replica_lag(lag_secs: f64) {
+    #[cfg(feature = "prometheus")]
+    gauge!("db_replica_lag_seconds").set(lag_secs);
+    #[cfg(not(feature = "prometheus"))]
+    let _ = lag_secs;
+}
+
 /// Record circuit breaker state change.
 ///
 /// Tracks both the current state as a gauge (0=closed, 1=open, 2=half_open)
@@ -874,7 +1272,7 @@ pub fn record_circuit_breaker_state(provider: &str, state: &str) {
             _ => 0.0,
         };
 
-        gauge!("provider_circuit_breaker_state", "provider" => provider.to_string())
+        gauge!(names::PROVIDER_CIRCUIT_BREAKER_STATE, "provider" => provider.to_string())
             .set(state_value);
 
         counter!("provider_circuit_breaker_transitions_total", "provider" => provider.to_string(), "state" => state.to_string())
@@ -920,6 +1318,137 @@ pub fn record_circuit_breaker_consecutive_opens(provider: &str, consecutive_open
     let _ = (provider, consecutive_opens);
 }
 
+/// Record a provider's health-based routing weight (0 to 1).
+pub fn record_provider_routing_weight(provider: &str, weight: f64) {
+    #[cfg(feature = "prometheus")]
+    gauge!("provider_routing_weight", "provider" => provider.to_string()).set(weight);
+    #[cfg(not(feature = "prometheus"))]
+    let _ = (provider, weight);
+}
+
+/// Record a synthetic provider probe.
+///
+/// `outcome` is `correct`, `incorrect` (completion without the expected
+/// answer) or `error`.
+pub fn record_provider_probe(provider: &str, model: &str, outcome: &str, latency_secs: f64) {
+    #[cfg(feature = "prometheus")]
+    {
+        counter!("provider_probes_total", "provider" => provider.to_string(), "model" => model.to_string(), "outcome" => outcome.to_string())
+            .increment(1);
+        histogram!("provider_probe_duration_seconds", "provider" => provider.to_string(), "model" => model.to_string())
+            .record(latency_secs);
+    }
+    #[cfg(not(feature = "prometheus"))]
+    let _ = (provider, model, outcome, latency_secs);
+}
+
+/// Record a request sent to `target` instead of a degraded provider.
+pub fn record_provider_routing_diversion(provider: &str, target: &str) {
+    #[cfg(feature = "prometheus")]
+    counter!("provider_routing_diversions_total", "provider" => provider.to_string(), "target" => target.to_string())
+        .increment(1);
+    #[cfg(not(feature = "prometheus"))]
+    let _ = (provider, target);
+}
+
+/// Record a shared circuit breaker sync with the cache.
+///
+/// `outcome` is `published` (this node's open written to the cache),
+/// `applied` (another node's open applied here), or `error`.
+pub fn record_circuit_breaker_sync(provider: &str, outcome: &str) {
+    #[cfg(feature = "prometheus")]
+    counter!("provider_circuit_breaker_syncs_total", "provider" => provider.to_string(), "outcome" => outcome.to_string())
+        .increment(1);
+    #[cfg(not(feature = "prometheus"))]
+    let _ = (provider, outcome);
+}
+
+/// Record how a request fared in a provider's fair queue.
+///
+/// `priority` is the priority it queued at. `outcome` is `immediate` (slot
+/// free, no wait), `granted` (waited for a slot), `timeout`, or `rejected`
+/// (queue full).
+pub fn record_fair_queue_wait(provider: &str, priority: &str, outcome: &str, wait_secs: f64) {
+    #[cfg(feature = "prometheus")]
+    {
+        counter!("provider_fair_queue_requests_total", "provider" => provider.to_string(), "priority" => priority.to_string(), "outcome" => outcome.to_string())
+            .increment(1);
+        if outcome != "immediate" {
+            histogram!("provider_fair_queue_wait_seconds", "provider" => provider.to_string(), "priority" => priority.to_string())
+                .record(wait_secs);
+        }
+    }
+    #[cfg(not(feature = "prometheus"))]
+    {
+        let _ = (provider, priority, outcome, wait_secs);
+    }
+}
+
+/// Record the number of requests waiting at a priority in a provider's fair
+/// queue.
+pub fn record_fair_queue_depth(provider: &str, priority: &str, depth: usize) {
+    #[cfg(feature = "prometheus")]
+    gauge!("provider_fair_queue_depth", "provider" => provider.to_string(), "priority" => priority.to_string())
+        .set(depth as f64);
+    #[cfg(not(feature = "prometheus"))]
+    let _ = (provider, priority, depth);
+}
+
+/// Record a provider's current adaptive concurrency limit.
+pub fn set_provider_concurrency_limit(provider: &str, limit: u32) {
+    #[cfg(feature = "prometheus")]
+    gauge!("provider_concurrency_limit", "provider" => provider.to_string()).set(limit as f64);
+    #[cfg(not(feature = "prometheus"))]
+    let _ = (provider, limit);
+}
+
+/// Record a cut to a provider's adaptive concurrency limit.
+///
+/// `reason` is `rate_limited`, `unavailable`, `timeout` or `latency`.
+pub fn record_provider_concurrency_backoff(provider: &str, reason: &str) {
+    #[cfg(feature = "prometheus")]
+    counter!("provider_concurrency_backoffs_total", "provider" => provider.to_string(), "reason" => reason.to_string())
+        .increment(1);
+    #[cfg(not(feature = "prometheus"))]
+    let _ = (provider, reason);
+}
+
+/// Record a request that timed out waiting under a provider's adaptive
+/// concurrency limit.
+pub fn record_provider_concurrency_rejected(provider: &str) {
+    #[cfg(feature = "prometheus")]
+    counter!("provider_concurrency_rejected_total", "provider" => provider.to_string())
+        .increment(1);
+    #[cfg(not(feature = "prometheus"))]
+    let _ = provider;
+}
+
+/// Record how a request fared in per-key burst smoothing.
+///
+/// `outcome` is `delayed` (held until a token was available) or `rejected`
+/// (the wait would have exceeded `max_delay_ms`). Requests admitted without
+/// waiting aren't counted.
+pub fn record_rate_limit_smoothing(outcome: &str, delay_secs: f64) {
+    #[cfg(feature = "prometheus")]
+    {
+        counter!("rate_limit_smoothing_total", "outcome" => outcome.to_string()).increment(1);
+        if outcome == "delayed" {
+            histogram!("rate_limit_smoothing_delay_seconds").record(delay_secs);
+        }
+    }
+    #[cfg(not(feature = "prometheus"))]
+    {
+        let _ = (outcome, delay_secs);
+    }
+}
+
+/// Record a token bucket check that fell back to a local bucket because
+/// Redis was unavailable.
+pub fn record_token_bucket_fallback() {
+    #[cfg(feature = "prometheus")]
+    counter!("rate_limit_token_bucket_fallbacks_total").increment(1);
+}
+
 /// Record a gateway error with categorization.
 ///
 /// Provides a unified counter for all gateway errors, enabling:
@@ -1018,6 +1547,29 @@ pub fn record_fallback_attempt(
     }
 }
 
+/// Record the outcome of a request to a model with hedging configured.
+///
+/// `outcome` is `not_sent` (the primary answered within the hedge delay),
+/// `primary` or `hedge` (whichever answered first once both were in flight),
+/// or `failed` (neither returned a usable response).
+pub fn record_hedged_request(provider: &str, model: &str, hedge_provider: &str, outcome: &str) {
+    #[cfg(feature = "prometheus")]
+    {
+        counter!(
+            "provider_hedged_requests_total",
+            "provider" => provider.to_string(),
+            "model" => model.to_string(),
+            "hedge_provider" => hedge_provider.to_string(),
+            "outcome" => outcome.to_string()
+        )
+        .increment(1);
+    }
+    #[cfg(not(feature = "prometheus"))]
+    {
+        let _ = (provider, model, hedge_provider, outcome);
+    }
+}
+
 /// Record when fallback chain is exhausted (all fallbacks failed).
 ///
 /// This is a critical error metric - indicates all providers are unavailable.
@@ -1186,6 +1738,45 @@ pub fn record_guardrails_error(provider: &str, stage: &str, error_type: &str) {
     }
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// SLO Metrics
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Record a request counted towards an SLO.
+///
+/// `result` is `"good"` or `"bad"`. Burn rates aggregated across replicas are
+/// computed from this counter in the generated alert rules.
+pub fn record_slo_event(slo: &str, result: &str) {
+    #[cfg(feature = "prometheus")]
+    counter!(names::SLO_EVENTS_TOTAL, "slo" => slo.to_string(), "result" => result.to_string())
+        .increment(1);
+    #[cfg(not(feature = "prometheus"))]
+    let _ = (slo, result);
+}
+
+/// Update an SLO's compliance and remaining error budget over its window.
+pub fn set_slo_compliance(slo: &str, compliance: f64, error_budget_remaining: f64) {
+    #[cfg(feature = "prometheus")]
+    {
+        gauge!(names::SLO_COMPLIANCE_RATIO, "slo" => slo.to_string()).set(compliance);
+        gauge!(names::SLO_ERROR_BUDGET_REMAINING, "slo" => slo.to_string())
+            .set(error_budget_remaining);
+    }
+    #[cfg(not(feature = "prometheus"))]
+    {
+        let _ = (slo, compliance, error_budget_remaining);
+    }
+}
+
+/// Update an SLO's burn rate over one of its alerting windows.
+pub fn set_slo_burn_rate(slo: &str, window: &str, burn_rate: f64) {
+    #[cfg(feature = "prometheus")]
+    gauge!(names::SLO_BURN_RATE, "slo" => slo.to_string(), "window" => window.to_string())
+        .set(burn_rate);
+    #[cfg(not(feature = "prometheus"))]
+    let _ = (slo, window, burn_rate);
+}
+
 /// Metrics initialization errors.
 #[derive(Debug, thiserror::Error)]
 pub enum MetricsError {
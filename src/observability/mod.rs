@@ -5,10 +5,14 @@
 //! - OpenTelemetry distributed tracing with OTLP export
 //! - Prometheus metrics with custom histograms for latency and tokens
 //! - SIEM integration for enterprise security monitoring
+//! - Generated Grafana dashboards and Prometheus alert rules
+//! - Latency and availability SLOs with rolling compliance and burn rates
 
+pub mod grafana;
 pub mod metrics;
 #[cfg(feature = "server")]
 pub mod siem;
+pub mod slo;
 #[cfg(feature = "server")]
 mod tracing_init;
 
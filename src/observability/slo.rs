@@ -0,0 +1,422 @@
+//! Latency and availability SLO tracking.
+//!
+//! Each finished LLM request is counted as good or bad for every configured
+//! [`SloConfig`] it matches, in one-minute buckets covering the SLO's window.
+//! From these, [`SloTracker::status`] derives rolling compliance and burn
+//! rates over the multiwindow alerting pairs: 5m and 1h for fast burn, 30m
+//! and 6h for slow burn.
+//!
+//! The tracker lives in process, so its compliance and burn rates reflect
+//! this replica only. The `slo_events_total` counter it also records is what
+//! the generated alert rules aggregate across replicas.
+
+use std::{collections::VecDeque, sync::OnceLock, time::Duration};
+
+use serde::Serialize;
+
+use super::metrics;
+use crate::{
+    compat::Mutex,
+    config::{SloConfig, SloIndicator},
+};
+
+/// Width of a counting bucket.
+const BUCKET_SECS: u64 = 60;
+
+/// Burn rate windows, as `(label, seconds)`.
+pub const BURN_RATE_WINDOWS: [(&str, u64); 4] =
+    [("5m", 300), ("30m", 1800), ("1h", 3600), ("6h", 21600)];
+
+/// Burn rate that both the 5m and 1h windows must exceed for a fast burn.
+/// At this rate, a 30-day budget loses 2% in an hour.
+pub const FAST_BURN_THRESHOLD: f64 = 14.4;
+
+/// Burn rate that both the 30m and 6h windows must exceed for a slow burn.
+/// At this rate, a 30-day budget loses 5% in six hours.
+pub const SLOW_BURN_THRESHOLD: f64 = 6.0;
+
+/// Process-wide tracker, set once at startup when SLOs are configured.
+static SLO_TRACKER: OnceLock<SloTracker> = OnceLock::new();
+
+/// Start tracking the configured SLOs. Does nothing when there are none.
+pub fn init_slos(slos: &[SloConfig]) {
+    if slos.is_empty() {
+        return;
+    }
+    if SLO_TRACKER.set(SloTracker::new(slos.to_vec())).is_err() {
+        tracing::warn!("SLO tracking already initialized");
+    }
+}
+
+/// The process-wide tracker, if any SLOs are configured.
+pub fn tracker() -> Option<&'static SloTracker> {
+    SLO_TRACKER.get()
+}
+
+/// A finished LLM request, as seen by SLOs.
+#[derive(Debug, Clone, Copy)]
+pub struct SloObservation<'a> {
+    pub provider: &'a str,
+    pub model: &'a str,
+    /// Time to first token, for streamed responses that produced one.
+    pub ttft: Option<Duration>,
+    /// Total duration, for requests that ran to completion.
+    pub latency: Option<Duration>,
+    /// Whether the request failed with a server error or a broken stream.
+    pub failed: bool,
+}
+
+/// Count a request towards every SLO it matches. A no-op when no SLOs are
+/// configured.
+pub fn observe(observation: SloObservation<'_>) {
+    if let Some(tracker) = tracker() {
+        tracker.observe(&observation, now_secs());
+    }
+}
+
+fn now_secs() -> u64 {
+    chrono::Utc::now().timestamp().max(0) as u64
+}
+
+/// Whether a request was good (`Some(true)`), bad (`Some(false)`) or doesn't
+/// count (`None`) for an SLO.
+fn classify(config: &SloConfig, observation: &SloObservation<'_>) -> Option<bool> {
+    let within = |d: Duration| Some(d.as_millis() <= u128::from(config.threshold_ms?));
+    match config.indicator {
+        SloIndicator::Ttft => within(observation.ttft?),
+        SloIndicator::Latency if observation.failed => None,
+        SloIndicator::Latency => within(observation.latency?),
+        SloIndicator::Availability => Some(!observation.failed),
+    }
+}
+
+/// Requests counted in one bucket.
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    start: u64,
+    good: u64,
+    bad: u64,
+}
+
+struct TrackedSlo {
+    config: SloConfig,
+    /// Buckets in ascending `start` order, covering at most the window.
+    buckets: Mutex<VecDeque<Bucket>>,
+}
+
+impl TrackedSlo {
+    /// Good and bad counts for buckets overlapping the last `window_secs`.
+    fn counts(buckets: &VecDeque<Bucket>, now: u64, window_secs: u64) -> (u64, u64) {
+        let since = now.saturating_sub(window_secs);
+        buckets
+            .iter()
+            .rev()
+            .take_while(|b| b.start + BUCKET_SECS > since)
+            .fold((0, 0), |(good, bad), b| (good + b.good, bad + b.bad))
+    }
+
+    /// Bad fraction over the error budget, or `None` without traffic.
+    fn burn_rate(&self, good: u64, bad: u64) -> Option<f64> {
+        let total = good + bad;
+        (total > 0).then(|| (bad as f64 / total as f64) / (1.0 - self.config.objective))
+    }
+}
+
+/// Burn rate over one alerting window.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct SloBurnRate {
+    /// Window label (`5m`, `30m`, `1h`, `6h`).
+    pub window: String,
+    /// Bad fraction divided by the error budget. At `1.0` the budget runs out
+    /// exactly at the end of the SLO window. `null` without traffic.
+    pub burn_rate: Option<f64>,
+}
+
+/// Current state of one SLO.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct SloStatus {
+    pub name: String,
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    pub indicator: SloIndicator,
+    pub threshold_ms: Option<u64>,
+    pub objective: f64,
+    pub window_secs: u64,
+    /// Requests counted over the window.
+    pub total: u64,
+    /// Good requests over the window.
+    pub good: u64,
+    /// Fraction of good requests over the window. `null` without traffic.
+    pub compliance: Option<f64>,
+    /// Fraction of the error budget left over the window. Negative once the
+    /// budget is exhausted. `null` without traffic.
+    pub error_budget_remaining: Option<f64>,
+    pub burn_rates: Vec<SloBurnRate>,
+    /// Both the 5m and 1h burn rates exceed [`FAST_BURN_THRESHOLD`].
+    pub fast_burn: bool,
+    /// Both the 30m and 6h burn rates exceed [`SLOW_BURN_THRESHOLD`].
+    pub slow_burn: bool,
+}
+
+/// Rolling good/bad counts for the configured SLOs.
+pub struct SloTracker {
+    slos: Vec<TrackedSlo>,
+}
+
+impl SloTracker {
+    pub fn new(slos: Vec<SloConfig>) -> Self {
+        Self {
+            slos: slos
+                .into_iter()
+                .map(|config| TrackedSlo {
+                    config,
+                    buckets: Mutex::new(VecDeque::new()),
+                })
+                .collect(),
+        }
+    }
+
+    /// Count a request towards every SLO it matches, at `now` (unix seconds).
+    pub fn observe(&self, observation: &SloObservation<'_>, now: u64) {
+        let start = now - now % BUCKET_SECS;
+        for slo in &self.slos {
+            if !slo.config.matches(observation.provider, observation.model) {
+                continue;
+            }
+            let Some(good) = classify(&slo.config, observation) else {
+                continue;
+            };
+            metrics::record_slo_event(&slo.config.name, if good { "good" } else { "bad" });
+
+            // A request racing a minute boundary lands in the newer bucket
+            let mut buckets = slo.buckets.lock();
+            if buckets.back().is_none_or(|b| b.start < start) {
+                buckets.push_back(Bucket {
+                    start,
+                    good: 0,
+                    bad: 0,
+                });
+            }
+            if let Some(bucket) = buckets.back_mut() {
+                if good {
+                    bucket.good += 1;
+                } else {
+                    bucket.bad += 1;
+                }
+            }
+
+            let expired = now.saturating_sub(slo.config.window_secs);
+            while buckets
+                .front()
+                .is_some_and(|b| b.start + BUCKET_SECS <= expired)
+            {
+                buckets.pop_front();
+            }
+        }
+    }
+
+    /// Compliance and burn rates for every SLO, at `now` (unix seconds).
+    pub fn status(&self, now: u64) -> Vec<SloStatus> {
+        self.slos
+            .iter()
+            .map(|slo| {
+                let buckets = slo.buckets.lock();
+                let config = &slo.config;
+
+                let (good, bad) = TrackedSlo::counts(&buckets, now, config.window_secs);
+                let total = good + bad;
+                let compliance = (total > 0).then(|| good as f64 / total as f64);
+                let error_budget_remaining = slo.burn_rate(good, bad).map(|rate| 1.0 - rate);
+
+                let burn_rates: Vec<SloBurnRate> = BURN_RATE_WINDOWS
+                    .iter()
+                    .map(|&(window, secs)| {
+                        let (good, bad) = TrackedSlo::counts(&buckets, now, secs);
+                        SloBurnRate {
+                            window: window.to_string(),
+                            burn_rate: slo.burn_rate(good, bad),
+                        }
+                    })
+                    .collect();
+                let exceeds = |window: &str, threshold: f64| {
+                    burn_rates
+                        .iter()
+                        .find(|b| b.window == window)
+                        .and_then(|b| b.burn_rate)
+                        .is_some_and(|rate| rate > threshold)
+                };
+                let fast_burn =
+                    exceeds("5m", FAST_BURN_THRESHOLD) && exceeds("1h", FAST_BURN_THRESHOLD);
+                let slow_burn =
+                    exceeds("30m", SLOW_BURN_THRESHOLD) && exceeds("6h", SLOW_BURN_THRESHOLD);
+
+                SloStatus {
+                    name: config.name.clone(),
+                    provider: config.provider.clone(),
+                    model: config.model.clone(),
+                    indicator: config.indicator,
+                    threshold_ms: config.threshold_ms,
+                    objective: config.objective,
+                    window_secs: config.window_secs,
+                    total,
+                    good,
+                    compliance,
+                    error_budget_remaining,
+                    burn_rates,
+                    fast_burn,
+                    slow_burn,
+                }
+            })
+            .collect()
+    }
+
+    /// Compliance and burn rates for every SLO as of now.
+    pub fn current_status(&self) -> Vec<SloStatus> {
+        self.status(now_secs())
+    }
+
+    /// Publish current compliance and burn rates as Prometheus gauges.
+    /// SLOs without traffic in a window leave that gauge unchanged.
+    pub fn publish_metrics(&self) {
+        for status in self.current_status() {
+            if let (Some(compliance), Some(remaining)) =
+                (status.compliance, status.error_budget_remaining)
+            {
+                metrics::set_slo_compliance(&status.name, compliance, remaining);
+            }
+            for burn in &status.burn_rates {
+                if let Some(rate) = burn.burn_rate {
+                    metrics::set_slo_burn_rate(&status.name, &burn.window, rate);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 1_700_000_000;
+
+    fn slo(toml: &str) -> SloConfig {
+        toml::from_str(toml).unwrap()
+    }
+
+    fn ttft_slo() -> SloConfig {
+        slo(r#"
+            name = "gpt-4o-ttft"
+            model = "gpt-4o*"
+            indicator = "ttft"
+            threshold_ms = 800
+            objective = 0.95
+        "#)
+    }
+
+    fn streamed(model: &str, ttft_ms: u64) -> SloObservation<'_> {
+        SloObservation {
+            provider: "openai",
+            model,
+            ttft: Some(Duration::from_millis(ttft_ms)),
+            latency: Some(Duration::from_secs(3)),
+            failed: false,
+        }
+    }
+
+    #[test]
+    fn test_classify_by_indicator() {
+        let observation = streamed("gpt-4o", 900);
+        assert_eq!(classify(&ttft_slo(), &observation), Some(false));
+        assert_eq!(classify(&ttft_slo(), &streamed("gpt-4o", 800)), Some(true));
+
+        let latency = slo(r#"
+            name = "latency"
+            indicator = "latency"
+            threshold_ms = 5000
+            objective = 0.99
+        "#);
+        assert_eq!(classify(&latency, &observation), Some(true));
+        let failed = SloObservation {
+            failed: true,
+            ..observation
+        };
+        assert_eq!(classify(&latency, &failed), None);
+
+        let availability = slo(r#"
+            name = "availability"
+            indicator = "availability"
+            objective = 0.999
+        "#);
+        assert_eq!(classify(&availability, &observation), Some(true));
+        assert_eq!(classify(&availability, &failed), Some(false));
+
+        // Non-streamed responses have no time to first token
+        let non_streamed = SloObservation {
+            ttft: None,
+            ..observation
+        };
+        assert_eq!(classify(&ttft_slo(), &non_streamed), None);
+    }
+
+    #[test]
+    fn test_compliance_and_burn_rates() {
+        let tracker = SloTracker::new(vec![ttft_slo()]);
+
+        // An hour ago: 10 requests, all good
+        for _ in 0..10 {
+            tracker.observe(&streamed("gpt-4o-mini", 200), NOW - 3000);
+        }
+        // Just now: 5 good, 5 bad
+        for i in 0..10 {
+            let ttft = if i % 2 == 0 { 200 } else { 2000 };
+            tracker.observe(&streamed("gpt-4o", ttft), NOW);
+        }
+        // Other models don't count
+        tracker.observe(&streamed("claude-sonnet", 5000), NOW);
+
+        let status = &tracker.status(NOW)[0];
+        assert_eq!(status.total, 20);
+        assert_eq!(status.good, 15);
+        assert_eq!(status.compliance, Some(0.75));
+        // 25% bad against a 5% budget
+        let remaining = status.error_budget_remaining.unwrap();
+        assert!((remaining - -4.0).abs() < 1e-9);
+
+        let rate = |window: &str| {
+            status
+                .burn_rates
+                .iter()
+                .find(|b| b.window == window)
+                .unwrap()
+                .burn_rate
+                .unwrap()
+        };
+        assert!((rate("5m") - 10.0).abs() < 1e-9);
+        assert!((rate("1h") - 5.0).abs() < 1e-9);
+        assert!(!status.fast_burn);
+        assert!(!status.slow_burn);
+    }
+
+    #[test]
+    fn test_fast_burn_and_window_expiry() {
+        let tracker = SloTracker::new(vec![ttft_slo()]);
+        for _ in 0..10 {
+            tracker.observe(&streamed("gpt-4o", 5000), NOW);
+        }
+        let status = &tracker.status(NOW)[0];
+        assert!(status.fast_burn);
+        assert!(status.slow_burn);
+
+        // Past the 30-day window, nothing is counted
+        let later = NOW + 31 * 24 * 3600;
+        let status = &tracker.status(later)[0];
+        assert_eq!(status.total, 0);
+        assert_eq!(status.compliance, None);
+        assert!(status.burn_rates.iter().all(|b| b.burn_rate.is_none()));
+
+        tracker.observe(&streamed("gpt-4o", 100), later);
+        assert_eq!(tracker.slos[0].buckets.lock().len(), 1);
+    }
+}
@@ -4,7 +4,7 @@ use utoipa::OpenApi;
 
 #[cfg(feature = "utoipa")]
 use crate::{
-    api_types, models,
+    api_types, authz, models,
     routes::{admin, api, health},
 };
 
@@ -176,12 +176,37 @@ All errors follow a consistent JSON format:
 {
   \"error\": {
     \"code\": \"error_code\",
+    \"category\": \"rate_limited\",
     \"message\": \"Human-readable error message\",
     \"details\": { ... }  // Optional additional context
   }
 }
 ```
 
+### Error Categories
+
+`category` groups the specific `code` into a small, stable taxonomy shared by gateway errors and
+provider errors. Provider errors are mapped from each provider's native format (e.g. Anthropic
+`overloaded_error`, Bedrock `ThrottlingException`, Vertex `RESOURCE_EXHAUSTED`), so clients can
+branch on `category` without knowing which provider served the request. The same value is sent in
+the `X-Error-Category` header on provider errors and recorded as `error_code` in usage logs.
+
+| Category | Typical Status | Description |
+|----------|----------------|-------------|
+| `rate_limited` | 429 | Gateway or provider rate limit |
+| `quota_exceeded` | 429 | Provider account is out of credit or quota |
+| `budget_exceeded` | 402 | Gateway budget exceeded |
+| `content_filtered` | 400 | Blocked by a provider safety filter or gateway guardrail |
+| `context_length` | 400 | Input exceeds the model's context window |
+| `invalid_request` | 400 | Malformed request or unsupported parameter |
+| `authentication` | 401 | Missing or invalid credentials |
+| `permission_denied` | 403 | No access to the resource or model |
+| `not_found` | 404 | Unknown model or resource |
+| `provider_unavailable` | 502, 503 | Provider overloaded or unreachable, circuit breaker open, or fair queue full |
+| `timeout` | 504 | Provider did not respond in time |
+| `provider_error` | 500 | Other provider-side failure |
+| `internal` | 500 | Failure inside the gateway |
+
 ### Authentication & Authorization Errors
 
 | Code | HTTP Status | Description |
@@ -350,6 +375,8 @@ requests_per_minute = 120
         (name = "completions", description = "Create text completions from a prompt. Legacy API for non-chat models. OpenAI-compatible."),
         (name = "embeddings", description = "Generate vector embeddings for text input. Use for semantic search, clustering, and similarity comparisons. OpenAI-compatible."),
         (name = "models", description = "List all available models from configured providers. Model IDs are prefixed with provider name."),
+        (name = "mcp", description = "Model Context Protocol server (Streamable HTTP transport). Exposes file search and the model catalog as MCP tools and prompt templates as MCP prompts. Enabled with `[features.mcp_server]`."),
+        (name = "tokenize", description = "Count a request's prompt tokens with the model's own tokenizer (tiktoken for OpenAI models, Anthropic's counting API, and Hugging Face tokenizers for open models), and estimate its cost and limit headroom before sending it. Counting is enabled with `[features.token_counting]`."),
         (name = "me", description = "Self-service endpoints for authenticated users. Export personal data for GDPR compliance."),
         (name = "oauth", description = "OAuth-style PKCE flow for issuing user-scoped API keys to external apps. The user grants consent in the Hadrian UI; the external app exchanges the resulting code at `/oauth/token` for an API key bound to that user."),
         (name = "Images", description = "Generate, edit, and create variations of images using DALL-E models. OpenAI-compatible."),
@@ -361,16 +388,30 @@ requests_per_minute = 120
         (name = "api-keys", description = "API keys authenticate requests to the Public API. Keys can be scoped to organizations, projects, or users with optional budget limits and expiration."),
         (name = "dynamic-providers", description = "Dynamic providers allow runtime configuration of LLM backends without restarting the gateway. Useful for BYOK (bring-your-own-key) scenarios."),
         (name = "usage", description = "Query usage statistics for API keys including token counts, costs, and breakdowns by date, model, or referer."),
+        (name = "model-catalog", description = "Add or override model catalog entries for self-hosted or fine-tuned models. Entries take precedence over the models.dev catalog and their pricing is used for cost tracking when no pricing is configured."),
         (name = "model-pricing", description = "Configure per-model pricing for cost tracking. Pricing can be set globally, per-provider, per-organization, per-project, or per-user."),
         (name = "conversations", description = "Store and manage chat conversation history. Conversations can be owned by users or projects and support multiple models."),
         (name = "templates", description = "Manage reusable prompt templates. Templates can be owned by organizations, teams, projects, or users and include metadata for configuration."),
         (name = "skills", description = "Manage Skills (OpenAI-compatible `/v1/skills`). A skill packages a SKILL.md instruction file plus optional bundled scripts, references, and assets, published as immutable versions with a `default_version`/`latest_version` pointer. Upload as a JSON file array, a multipart directory, or a zip bundle; download a version as zip via `/content`.\n\n## Hadrian Extensions\n- `owner_type`/`owner_id` for organization/team/project/user ownership (OpenAI is project-scoped)\n- JSON `files` array (`{path, content}`) alongside the spec's zip/multipart upload\n- `files`/`files_manifest`, `total_bytes`, and frontmatter flags on responses\n- `skill_reference` accepts a prefixed/bare id or a name slug, plus a specific `version`"),
         (name = "audit-logs", description = "Query audit logs for admin operations. All sensitive operations like API key creation, user permission changes, and resource modifications are logged."),
+        (name = "reports", description = "Delivery history for scheduled reports configured under `[features.scheduled_reports]`. Each generation and delivery attempt is recorded with its outcome and rendered content."),
+        (name = "shadow-traffic", description = "Results of mirroring requests to candidate providers under `[features.shadow_traffic]`. Each mirrored request records both outputs, latency, tokens and cost; the report aggregates them per rule and provider/model pair."),
+        (name = "federation", description = "Multi-gateway federation. Satellite gateways push daily usage totals and provider health to a hub via `/federation/v1/reports`; the hub exposes the reporting gateways and cross-region usage here."),
+        (name = "reconciliation", description = "Provider-reported usage imported from OpenAI and Anthropic usage APIs or OpenAI, Anthropic and Azure CSV exports, and a per-day, per-model variance report against gateway usage."),
+        (name = "observability", description = "Grafana dashboard and Prometheus alert rules generated from the gateway's metric names, configured providers and SLOs, and SLO compliance status."),
+        (name = "jobs", description = "Background jobs such as retention, vector store cleanup, model catalog sync and DLQ retry. List the jobs running on the instance serving the request with their last and next runs, trigger them, pause and resume them, and read their run history."),
+        (name = "bundles", description = "Export organizations, teams, projects, memberships, API key metadata, RBAC policies and model pricing as one JSON or YAML bundle, and import it into another environment. Import creates and updates but never deletes, and `dry_run` reports the changes without applying them."),
+        (name = "system", description = "Lifecycle of the gateway instance serving the request, and which node runs each cluster-wide background job. Draining fails the readiness probe and turns away new data-plane requests while in-flight ones finish, for zero-downtime rolling deploys."),
         (name = "teams", description = "Teams group users within an organization for easier permission management. Users can belong to multiple teams, and projects can be assigned to a team."),
         (name = "service_accounts", description = "Service accounts are machine identities that can own API keys and carry roles for RBAC evaluation. They enable unified authorization across human users and automated systems."),
         (name = "access-reviews", description = "Access review reports for compliance requirements (SOC 2, ISO 27001). View user access across organizations, projects, and API keys."),
         (name = "sso", description = "SSO connection configuration (read-only from config). View OIDC and proxy auth settings for JIT user provisioning."),
         (name = "files", description = "Upload and manage files for use with vector stores. Files are uploaded via multipart form data and can be added to vector stores for RAG."),
+        (name = "fine-tuning", description = "Fine-tuning jobs (OpenAI-compatible `/v1/fine_tuning/jobs`), run on the OpenAI-compatible provider the base model routes to. Jobs belong to the caller's organization and project. Training files uploaded to the gateway are copied to the provider.\n\n## Hadrian Extensions\n- Trained tokens are recorded as usage, priced per `[features.fine_tuning.training_prices]`\n- Succeeded jobs' models are added to the model catalog and to the owning organization's and project's model allow lists\n- Listing returns only the caller's jobs, with statuses as of the last sync"),
+        (name = "agents", description = "Agents defined per organization through the admin API: a model with a system prompt, server-executed tools and vector stores. Running an agent with `/v1/agents/{agent_id}/runs` executes the agent's tool-use loop server-side, stores every output item as a step, and records the tokens and cost of all its turns. Model usage is logged with the run ID as its request ID."),
+        (name = "http-tools", description = "Custom HTTP tools registered per organization through the admin API. Requests made in the organization opt in by declaring a function tool with the tool's name; the gateway supplies the stored definition, POSTs the model's arguments to the tool's URL and feeds the response body back to the model. URLs must pass the gateway's SSRF checks, and header values are write-only."),
+        (name = "scheduled-tasks", description = "Recurring prompts defined per organization through the admin API. Each task sends its prompt to a model on a five-field cron schedule (UTC) and appends the answer to one of the organization's conversations or POSTs it to a webhook, signed with `X-Hadrian-Signature` when a signing secret is set. Every run is recorded with its output, tokens and cost; a task is disabled after repeated failures."),
+        (name = "provenance", description = "Verification of the signed provenance metadata returned with completion responses under `[features.provenance]`. The metadata carries the request ID, model, signing time and a SHA-256 of the generated text, signed with HMAC-SHA256; verification checks both the signature and that the text is unchanged."),
         (name = "vector-stores", description = "Create and manage vector stores for RAG (Retrieval Augmented Generation). Vector stores contain files that are chunked and embedded for semantic search.\n\n## Hadrian Extensions\n\nThe Vector Stores API is based on OpenAI's Vector Stores API with the following extensions:\n\n### Multi-Tenancy\n- `owner_type`, `owner_id` fields for organization/project/user ownership\n- Required in create requests and included in responses\n\n### Additional Fields\n- `description`: Human-readable description for vector stores\n- `embedding_model`: Configurable embedding model (default: text-embedding-3-small)\n- `embedding_dimensions`: Configurable vector dimensions (default: 1536)\n- `updated_at`: Modification timestamp\n- `file_id`: Reference to Files API in vector store files\n\n### Extension Endpoints\n- `GET /v1/vector_stores/{id}/files/{file_id}/chunks`: List chunks for debugging\n\n### Search Extensions\n- Request: `threshold` (similarity threshold), `file_ids` (file filter)\n- Response: `chunk_id`, `vector_store_id`, `chunk_index` for debugging\n\n### Schema Differences\n- Timestamps use ISO 8601 format (OpenAI uses Unix timestamps)\n- List responses use `pagination` object (OpenAI uses root-level `first_id`, `last_id`, `has_more`)\n- Search `content` is a string (OpenAI uses `[{type, text}]` array)"),
         // Health & Infrastructure
         (name = "health", description = "Health check endpoints for monitoring and Kubernetes probes. Use `/health` for detailed status, `/health/live` for liveness probes, and `/health/ready` for readiness probes."),
@@ -384,6 +425,7 @@ requests_per_minute = 120
         // Browser auth routes
         crate::routes::auth::discover,
         crate::routes::auth::me,
+        crate::routes::auth::accept_invitation,
         // Public API routes
         api::api_v1_chat_completions,
         api::api_v1_responses,
@@ -400,6 +442,17 @@ requests_per_minute = 120
         api::containers::api_v1_containers_file_get,
         api::containers::api_v1_containers_file_delete,
         api::containers::api_v1_containers_file_content,
+        // Public API - Fine-tuning
+        api::fine_tuning::api_v1_fine_tuning_jobs_create,
+        api::fine_tuning::api_v1_fine_tuning_jobs_list,
+        api::fine_tuning::api_v1_fine_tuning_jobs_get,
+        api::fine_tuning::api_v1_fine_tuning_jobs_cancel,
+        // Public API - Agents
+        api::agents::api_v1_agent_runs_create,
+        api::agents::api_v1_agent_runs_list,
+        api::agents::api_v1_agent_runs_get,
+        // Public API - Token exchange
+        api::token_exchange::api_v1_auth_token,
         // Public API - Skills
         api::skills::api_v1_skills_create,
         api::skills::api_v1_skills_list,
@@ -441,18 +494,51 @@ requests_per_minute = 120
         // Self-service endpoints - Sessions
         admin::me_sessions::list,
         admin::me_sessions::delete_one,
+        // Self-service endpoints - Step-up authentication
+        admin::step_up::status,
+        admin::step_up::enroll,
+        admin::step_up::confirm,
+        admin::step_up::remove,
+        admin::step_up::verify,
         // Admin routes - Organizations
         admin::organizations::create,
         admin::organizations::get,
         admin::organizations::list,
         admin::organizations::update,
         admin::organizations::delete,
+        admin::organizations::get_network_policy,
+        admin::organizations::set_network_policy,
+        admin::organizations::delete_network_policy,
+        admin::organizations::get_parameter_governance,
+        admin::organizations::set_parameter_governance,
+        admin::organizations::delete_parameter_governance,
+        admin::organizations::get_model_access,
+        admin::organizations::set_model_access,
+        admin::organizations::delete_model_access,
+        admin::organizations::get_model_degradation,
+        admin::organizations::set_model_degradation,
+        admin::organizations::delete_model_degradation,
+        admin::organizations::get_entitlements,
+        admin::organizations::set_entitlements,
+        admin::organizations::delete_entitlements,
+        admin::organizations::set_labels,
+        admin::organizations::allowed_models,
+        // Admin routes - Semantic cache
+        admin::semantic_cache::get,
+        admin::semantic_cache::purge,
         // Admin routes - Projects
         admin::projects::create,
         admin::projects::get,
         admin::projects::list,
         admin::projects::update,
+        admin::projects::set_labels,
         admin::projects::delete,
+        admin::projects::get_parameter_governance,
+        admin::projects::set_parameter_governance,
+        admin::projects::delete_parameter_governance,
+        admin::projects::get_model_access,
+        admin::projects::set_model_access,
+        admin::projects::delete_model_access,
         // Admin routes - Users
         admin::users::create,
         admin::users::get,
@@ -464,12 +550,15 @@ requests_per_minute = 120
         admin::sessions::list,
         admin::sessions::delete_all,
         admin::sessions::delete_one,
+        admin::sessions::force_logout,
         admin::users::list_org_members,
         admin::users::add_org_member,
         admin::users::remove_org_member,
         admin::users::list_project_members,
         admin::users::add_project_member,
         admin::users::remove_project_member,
+        admin::bulk::create_users,
+        admin::bulk::add_org_members,
         // Admin routes - API Keys
         admin::api_keys::create,
         admin::api_keys::list_by_org,
@@ -478,10 +567,13 @@ requests_per_minute = 120
         admin::api_keys::list_by_service_account,
         admin::api_keys::revoke,
         admin::api_keys::rotate,
+        admin::api_keys::set_labels,
+        admin::bulk::revoke_api_keys,
         // Admin routes - Dynamic Providers
         admin::dynamic_providers::create,
         admin::dynamic_providers::get,
         admin::dynamic_providers::update,
+        admin::dynamic_providers::set_labels,
         admin::dynamic_providers::delete,
         admin::dynamic_providers::list_by_org,
         admin::dynamic_providers::list_by_project,
@@ -500,6 +592,9 @@ requests_per_minute = 120
         admin::usage::get_org_by_model,
         admin::usage::get_org_by_provider,
         admin::usage::get_org_forecast,
+        admin::usage::get_org_reliability,
+        admin::usage::get_org_by_tag,
+        admin::usage::get_org_by_date_tag,
         // Admin routes - Usage (API Key by-provider and time series)
         admin::usage::get_by_provider,
         admin::usage::get_by_date_model,
@@ -516,6 +611,9 @@ requests_per_minute = 120
         admin::usage::get_project_by_pricing_source,
         admin::usage::get_project_by_date_pricing_source,
         admin::usage::get_project_forecast,
+        admin::usage::get_project_reliability,
+        admin::usage::get_project_by_tag,
+        admin::usage::get_project_by_date_tag,
         // Admin routes - Usage (User level)
         admin::usage::get_user_summary,
         admin::usage::get_user_by_date,
@@ -578,6 +676,10 @@ requests_per_minute = 120
         admin::usage::get_global_by_date_team,
         admin::usage::get_global_by_org,
         admin::usage::get_global_by_date_org,
+        admin::usage::get_reconciliation,
+        admin::usage::get_global_reliability,
+        admin::usage::get_global_by_tag,
+        admin::usage::get_global_by_date_tag,
         // Admin routes - Usage (Self-service)
         admin::usage::get_me_summary,
         admin::usage::get_me_by_date,
@@ -592,6 +694,12 @@ requests_per_minute = 120
         admin::usage::list_me_logs,
         admin::usage::export_logs,
         admin::usage::export_me_logs,
+        // Admin routes - Model Catalog
+        admin::model_catalog::list,
+        admin::model_catalog::create,
+        admin::model_catalog::get,
+        admin::model_catalog::update,
+        admin::model_catalog::delete,
         // Admin routes - Model Pricing
         admin::model_pricing::create,
         admin::model_pricing::get,
@@ -614,10 +722,16 @@ requests_per_minute = 120
         admin::conversations::list_by_project,
         admin::conversations::list_by_user,
         admin::conversations::list_accessible_for_user,
+        admin::conversations::list_branches,
+        admin::conversations::fork,
+        admin::conversations::switch_branch,
+        admin::conversations::get_attachment,
+        admin::conversations::replay,
         // Admin routes - Templates
         admin::templates::create,
         admin::templates::get,
         admin::templates::update,
+        admin::templates::set_labels,
         admin::templates::delete,
         admin::templates::list_by_org,
         admin::templates::list_by_team,
@@ -626,22 +740,71 @@ requests_per_minute = 120
         // Admin routes - Provider Management
         admin::providers::list_circuit_breakers,
         admin::providers::get_circuit_breaker,
+        admin::providers::get_concurrency,
+        admin::providers::get_provider_settings,
+        admin::providers::update_provider_settings,
+        admin::providers::delete_provider_settings,
         admin::providers::list_provider_health,
         admin::providers::get_provider_health,
         admin::providers::list_provider_stats,
         admin::providers::get_provider_stats,
         admin::providers::get_provider_stats_history,
+        admin::observability::grafana,
+        admin::observability::slos,
+        admin::system::drain_status,
+        admin::system::drain,
+        admin::system::jobs,
+        // Admin routes - Background jobs
+        admin::jobs::list,
+        admin::jobs::get,
+        admin::jobs::list_runs,
+        admin::jobs::run,
+        admin::jobs::pause,
+        admin::jobs::resume,
+        // Admin routes - Bundles
+        admin::bundles::export,
+        admin::bundles::import,
         // Admin routes - Dead Letter Queue
         admin::dlq::list,
         admin::dlq::get,
         admin::dlq::delete,
         admin::dlq::retry,
+        admin::dlq::redrive,
         admin::dlq::stats,
         admin::dlq::purge,
         admin::dlq::prune,
         // Admin routes - Audit Logs
         admin::audit_logs::list,
         admin::audit_logs::get,
+        admin::email_log::list,
+        // Admin routes - Scheduled Reports
+        admin::report_runs::list,
+        admin::report_runs::get,
+        admin::vector_store_syncs::list,
+        admin::vector_store_syncs::get,
+        // Admin routes - Response Replay
+        admin::replay::replay,
+        admin::request_tail::tail,
+        // Admin routes - Shadow Traffic
+        admin::shadow_results::list,
+        admin::shadow_results::report,
+        admin::shadow_results::get,
+        admin::shadow_results::delete,
+        admin::usage_anomalies::list,
+        admin::usage_anomalies::get,
+        admin::usage_anomalies::acknowledge,
+        // Federation
+        crate::routes::federation::ingest_report,
+        admin::federation::list_gateways,
+        admin::federation::get_gateway,
+        admin::federation::delete_gateway,
+        admin::federation::usage_summary,
+        // Admin routes - Provider usage reconciliation
+        admin::reconciliation::report,
+        admin::reconciliation::list_imports,
+        admin::reconciliation::import_from_api,
+        admin::reconciliation::import_csv,
+        admin::reconciliation::delete_import,
         // Admin routes - Access Reviews
         admin::access_reviews::get_inventory,
         admin::access_reviews::get_stale_access,
@@ -663,6 +826,20 @@ requests_per_minute = 120
         admin::service_accounts::list,
         admin::service_accounts::update,
         admin::service_accounts::delete,
+        admin::client_cert_mappings::create,
+        admin::client_cert_mappings::list,
+        admin::client_cert_mappings::delete,
+        admin::invitations::create,
+        admin::invitations::list,
+        admin::invitations::get,
+        admin::invitations::resend,
+        admin::invitations::revoke,
+        admin::elevations::create,
+        admin::elevations::list,
+        admin::elevations::get,
+        admin::elevations::approve,
+        admin::elevations::deny,
+        admin::elevations::revoke,
         // Admin routes - SSO Connections (read-only, from config)
         admin::sso_connections::list,
         admin::sso_connections::get,
@@ -682,6 +859,9 @@ requests_per_minute = 120
         admin::org_sso_configs::create,
         admin::org_sso_configs::update,
         admin::org_sso_configs::delete,
+        admin::org_sso_configs::get_role_rules,
+        admin::org_sso_configs::set_role_rules,
+        admin::org_sso_configs::evaluate_role_rules,
         // SAML metadata endpoints are conditionally added at runtime via merge_saml_openapi()
         // when the saml feature is enabled (parse_saml_metadata, get_sp_metadata)
         // Admin routes - Organization RBAC Policies
@@ -694,6 +874,33 @@ requests_per_minute = 120
         admin::org_rbac_policies::rollback,
         admin::org_rbac_policies::simulate,
         admin::org_rbac_policies::validate,
+        // Admin routes - Agents
+        admin::agents::list,
+        admin::agents::create,
+        admin::agents::get,
+        admin::agents::update,
+        admin::agents::delete,
+        // Admin routes - HTTP Tools
+        admin::http_tools::list,
+        admin::http_tools::create,
+        admin::http_tools::get,
+        admin::http_tools::update,
+        admin::http_tools::delete,
+        // Admin routes - Scheduled Tasks
+        admin::scheduled_tasks::list,
+        admin::scheduled_tasks::create,
+        admin::scheduled_tasks::get,
+        admin::scheduled_tasks::update,
+        admin::scheduled_tasks::delete,
+        admin::scheduled_tasks::list_runs,
+        // Admin routes - Provenance
+        admin::provenance::verify,
+        admin::org_request_policies::list,
+        admin::org_request_policies::create,
+        admin::org_request_policies::get,
+        admin::org_request_policies::update,
+        admin::org_request_policies::delete,
+        admin::org_request_policies::simulate,
         // Admin routes - Domain Verifications
         admin::domain_verifications::list,
         admin::domain_verifications::create,
@@ -737,12 +944,18 @@ requests_per_minute = 120
         api::api_v1_vector_stores_get_file_batch,
         api::api_v1_vector_stores_cancel_file_batch,
         api::api_v1_vector_stores_list_batch_files,
+        api::api_v1_vector_stores_get_file_content,
         // API routes - Vector Store Chunks & Search (Hadrian extensions)
         api::api_v1_vector_stores_list_file_chunks,
         api::api_v1_vector_stores_search,
         // API routes - Tools (Hadrian extensions)
         api::web_search,
         api::web_fetch,
+        // API routes - Token counting (Hadrian extension)
+        api::api_v1_tokenize,
+        api::api_v1_estimate,
+        // API routes - MCP server (Hadrian extension)
+        api::mcp::api_v1_mcp,
     ),
     components(schemas(
         // API types - Chat Completion
@@ -819,6 +1032,10 @@ requests_per_minute = 120
         // API types - Responses
         api_types::CreateResponsesPayload,
         api_types::CompactRequest,
+        // API types - Fine-tuning
+        api_types::CreateFineTuningJobRequest,
+        api_types::FineTuningJob,
+        api_types::FineTuningJobList,
         // API types - Containers
         api::containers::CreateContainerRequest,
         api_types::responses::ContainerExpiresAfter,
@@ -860,6 +1077,18 @@ requests_per_minute = 120
         models::Organization,
         models::CreateOrganization,
         models::UpdateOrganization,
+        models::OrgNetworkPolicy,
+        models::ParameterGovernance,
+        models::ParameterGovernanceRule,
+        models::GovernanceViolationAction,
+        models::ModelAccessPolicy,
+        models::ModelDegradationPolicy,
+        models::DegradationLadder,
+        models::DegradationTrigger,
+        models::OrgEntitlements,
+        models::Entitlements,
+        models::EntitlementUsage,
+        models::SetLabels,
         // Admin models - Project
         models::Project,
         models::CreateProject,
@@ -942,6 +1171,18 @@ requests_per_minute = 120
         admin::usage::UsageLogResponse,
         admin::usage::UsageLogListResponse,
         admin::usage::UsageLogExportFormat,
+        admin::usage::UsageReconciliationResponse,
+        crate::services::usage_reconciliation::ProviderReconciliation,
+        crate::services::usage_reconciliation::ReconciliationRow,
+        crate::services::usage_reconciliation::ReconciliationCounts,
+        crate::services::usage_reconciliation::UsageVariance,
+        admin::usage::UsageReliabilityResponse,
+        crate::services::usage_reliability::ReliabilityDimension,
+        crate::services::usage_reliability::ReliabilityStats,
+        crate::services::usage_reliability::ReliabilityRow,
+        admin::usage::TagUsageQuery,
+        admin::usage::TagSpendResponse,
+        admin::usage::DailyTagSpendResponse,
         // Admin routes - Users
         admin::users::AddMemberRequest,
         admin::users::UserListResponse,
@@ -949,14 +1190,39 @@ requests_per_minute = 120
         admin::sessions::SessionInfo,
         admin::sessions::SessionListResponse,
         admin::sessions::SessionsRevokedResponse,
+        admin::sessions::ForceLogoutRequest,
+        admin::sessions::ForceLogoutResponse,
         crate::auth::session_store::DeviceInfo,
+        // Self-service - Step-up authentication
+        admin::step_up::StepUpStatus,
+        admin::step_up::TotpEnrollmentResponse,
+        admin::step_up::TotpCodeRequest,
+        admin::step_up::StepUpVerifyResponse,
         // Admin routes - Organizations
         admin::organizations::ListQuery,
+        admin::organizations::LabelQuery,
         admin::organizations::OrganizationListResponse,
+        admin::organizations::AllowedModel,
+        admin::organizations::AllowedModelsResponse,
+        admin::organizations::OrgEntitlementsResponse,
+        admin::semantic_cache::SemanticCacheQuery,
+        admin::semantic_cache::SemanticCacheEntry,
+        admin::semantic_cache::SimilarityBucket,
+        admin::semantic_cache::SemanticCacheStatsResponse,
+        admin::semantic_cache::SemanticCacheResponse,
+        admin::semantic_cache::SemanticCachePurgeResponse,
         // Admin routes - Projects
         admin::projects::ProjectListResponse,
         // Admin routes - Model Pricing
         admin::model_pricing::BulkUpsertResponse,
+        // Admin routes - Bulk operations
+        admin::bulk::BulkCreateUsersRequest,
+        admin::bulk::BulkAddMembersRequest,
+        admin::bulk::BulkRevokeApiKeysRequest,
+        admin::bulk::BulkItemStatus,
+        admin::bulk::BulkItemError,
+        admin::bulk::BulkItemResult,
+        admin::bulk::BulkResponse,
         // Admin models - Conversation
         models::Conversation,
         models::ConversationWithProject,
@@ -964,12 +1230,17 @@ requests_per_minute = 120
         models::UpdateConversation,
         models::SetPinOrder,
         models::AppendMessages,
+        models::ConversationBranch,
+        models::ForkConversation,
+        models::SwitchConversationBranch,
+        models::MessageAttachment,
         models::ConversationOwner,
         models::ConversationOwnerType,
         models::Message,
         admin::conversations::ConversationListResponse,
         admin::conversations::ConversationWithProjectListResponse,
         admin::conversations::ListAccessibleQuery,
+        admin::conversations::ConversationReplay,
         // Admin models - Template
         models::Template,
         models::CreateTemplate,
@@ -994,30 +1265,144 @@ requests_per_minute = 120
         api::skills::CreateSkillBody,
         api::skills::CreateSkillVersionBody,
         api::skills::SetDefaultSkillVersionBody,
+        // Public API - Token exchange
+        api::token_exchange::TokenExchangeRequest,
+        api::token_exchange::TokenExchangeResponse,
+        // Admin routes - Background jobs
+        admin::jobs::JobListResponse,
+        admin::jobs::JobRunsQuery,
+        admin::jobs::JobRunListResponse,
+        crate::jobs::JobStatus,
+        models::JobRun,
+        models::JobTrigger,
+        models::JobRunStatus,
         // Admin routes - DLQ
         admin::dlq::DlqListQuery,
         admin::dlq::DlqEntryResponse,
         admin::dlq::DlqStatsResponse,
         admin::dlq::DlqRetryResponse,
+        admin::dlq::DlqRetryRequest,
+        admin::dlq::DlqRedriveRequest,
         admin::dlq::PruneQuery,
+        crate::dlq::redrive::DlqRedriveFilter,
+        crate::dlq::redrive::RedriveSummary,
+        crate::dlq::redrive::RedriveFailure,
+        crate::config::DlqErrorClass,
         // Admin routes - Providers
         admin::providers::CircuitBreakersResponse,
         admin::providers::ProviderCircuitBreakerResponse,
         admin::providers::ProviderHealthResponse,
         admin::providers::ProviderStatsResponse,
         admin::providers::ProviderStatsHistoryQuery,
+        admin::providers::ProviderSettingsResponse,
+        models::ProviderSettings,
+        models::UpdateProviderSettings,
+        models::CircuitBreakerSettings,
+        models::RetrySettings,
+        // Admin routes - Model Catalog
+        models::ModelCatalogEntry,
+        models::CreateModelCatalogEntry,
+        models::UpdateModelCatalogEntry,
+        models::CatalogEntryMetadata,
+        crate::catalog::ModelCapabilities,
+        crate::catalog::ModelModalities,
+        crate::catalog::ModelLimits,
+        crate::catalog::CatalogPricing,
+        admin::observability::GrafanaProvisioningResponse,
+        admin::observability::SlosResponse,
+        crate::middleware::DrainStatus,
+        admin::system::SystemJobsResponse,
+        models::JobLeader,
+        models::Bundle,
+        models::BundleUser,
+        models::BundleMember,
+        models::BundleOrganization,
+        models::BundleTeam,
+        models::BundleProject,
+        models::BundleRbacPolicy,
+        models::BundleApiKey,
+        models::BundleApiKeyOwner,
+        models::BundleModelPricing,
+        models::BundleFormat,
+        models::ExportBundleQuery,
+        models::ImportBundleQuery,
+        models::ImportAction,
+        models::ImportChange,
+        models::ImportReport,
+        crate::observability::slo::SloStatus,
+        crate::observability::slo::SloBurnRate,
+        crate::config::SloIndicator,
         crate::providers::CircuitBreakerStatus,
+        crate::providers::adaptive_concurrency::ConcurrencyStatus,
         crate::jobs::ProviderHealthState,
         crate::providers::health_check::HealthStatus,
         crate::services::ProviderStats,
         crate::services::ProviderStatsHistorical,
         crate::services::TimeBucketStats,
         crate::services::StatsGranularity,
+        models::ProbeBucketStats,
         // Admin routes - Audit Logs
         admin::audit_logs::AuditLogListResponse,
         models::AuditLog,
         models::AuditLogQuery,
         models::AuditActorType,
+        // Admin routes - Email send log
+        admin::email_log::EmailLogQuery,
+        admin::email_log::EmailLogListResponse,
+        models::EmailLogEntry,
+        models::EmailKind,
+        models::EmailSendStatus,
+        // Admin routes - Scheduled Reports
+        admin::report_runs::ReportRunListQuery,
+        admin::report_runs::ReportRunListResponse,
+        admin::vector_store_syncs::VectorStoreSyncListQuery,
+        admin::vector_store_syncs::VectorStoreSyncListResponse,
+        admin::replay::ReplayRequest,
+        crate::services::replay::ReplayMode,
+        crate::services::replay::ReplayOutcome,
+        crate::services::replay::ReplayTarget,
+        crate::services::replay::ReplayResult,
+        crate::services::replay::ReplayDiff,
+        crate::services::replay::DiffLine,
+        crate::services::replay::DiffOp,
+        admin::shadow_results::ShadowResultFilterQuery,
+        admin::shadow_results::ShadowResultListQuery,
+        admin::shadow_results::ShadowResultListResponse,
+        admin::shadow_results::ShadowReportResponse,
+        admin::shadow_results::ShadowResultDetail,
+        admin::shadow_results::ShadowResultDeleteResponse,
+        admin::usage_anomalies::UsageAnomalyListQuery,
+        admin::usage_anomalies::UsageAnomalyListResponse,
+        models::ShadowResult,
+        models::UsageAnomaly,
+        models::AnomalyKind,
+        models::ShadowReportRow,
+        models::ReportRun,
+        models::ReportRunStatus,
+        models::VectorStoreSync,
+        models::VectorStoreSyncStatus,
+        crate::config::ReportKind,
+        crate::config::ReportFormat,
+        // Federation types
+        admin::federation::FederatedUsageQuery,
+        models::FederationReport,
+        models::FederatedUsage,
+        models::FederatedProviderHealth,
+        models::FederatedGateway,
+        models::FederatedUsageTotals,
+        models::FederatedGatewayUsage,
+        models::FederatedModelUsage,
+        models::FederatedDailyUsage,
+        models::FederatedUsageSummary,
+        // Provider usage import types
+        admin::reconciliation::ReconciliationQuery,
+        admin::reconciliation::ListUsageImportsQuery,
+        admin::reconciliation::CsvUsageImportQuery,
+        admin::reconciliation::ApiUsageImportRequest,
+        models::ProviderUsageImport,
+        models::UsageImportSource,
+        models::UsageImportMethod,
+        models::ImportedUsage,
         // Access Review types
         models::ExportFormat,
         models::AccessInventoryResponse,
@@ -1057,6 +1442,22 @@ requests_per_minute = 120
         models::CreateServiceAccount,
         models::UpdateServiceAccount,
         admin::service_accounts::ServiceAccountListResponse,
+        models::CertMatchType,
+        models::ClientCertMapping,
+        models::CreateClientCertMapping,
+        admin::client_cert_mappings::ClientCertMappingListResponse,
+        // Invitation types
+        models::Invitation,
+        models::InvitationStatus,
+        models::CreateInvitation,
+        admin::invitations::InvitationListResponse,
+        admin::invitations::InvitationWithLink,
+        // Role elevation types
+        models::RoleElevation,
+        models::ElevationStatus,
+        models::CreateRoleElevation,
+        models::ElevationDecision,
+        admin::elevations::RoleElevationListResponse,
         // SSO Connection types
         admin::sso_connections::SsoConnection,
         admin::sso_connections::SsoConnectionsResponse,
@@ -1090,6 +1491,14 @@ requests_per_minute = 120
         models::UpdateOrgSsoConfig,
         models::SsoProviderType,
         models::SsoEnforcementMode,
+        models::SsoRoleRule,
+        models::SsoRoleRules,
+        models::ClaimCondition,
+        models::ClaimOperator,
+        models::RoleRuleTarget,
+        models::ResolvedRoleRule,
+        admin::org_sso_configs::EvaluateRoleRulesRequest,
+        admin::org_sso_configs::EvaluateRoleRulesResponse,
         // Organization RBAC Policy types
         models::OrgRbacPolicy,
         models::OrgRbacPolicyVersion,
@@ -1107,6 +1516,44 @@ requests_per_minute = 120
         admin::org_rbac_policies::PolicySource,
         admin::org_rbac_policies::ValidateCelRequest,
         admin::org_rbac_policies::ValidateCelResponse,
+        // Organization Request Policy types
+        models::OrgRequestPolicy,
+        models::CreateOrgRequestPolicy,
+        models::UpdateOrgRequestPolicy,
+        models::RequestPolicyAction,
+        authz::RequestPolicyEvaluation,
+        authz::RequestPolicyDecision,
+        authz::RequestPolicyTraceEntry,
+        admin::agents::AgentListResponse,
+        models::Agent,
+        models::CreateAgent,
+        models::UpdateAgent,
+        models::AgentRun,
+        models::AgentRunStatus,
+        models::AgentRunStep,
+        models::AgentRunWithSteps,
+        models::AgentRunList,
+        models::CreateAgentRunRequest,
+        admin::http_tools::HttpToolListResponse,
+        models::HttpTool,
+        models::CreateHttpTool,
+        models::UpdateHttpTool,
+        admin::scheduled_tasks::ScheduledTaskListResponse,
+        admin::scheduled_tasks::ScheduledTaskRunListResponse,
+        models::ScheduledTask,
+        models::ScheduledTaskDelivery,
+        models::CreateScheduledTask,
+        models::UpdateScheduledTask,
+        models::ScheduledTaskRun,
+        models::ScheduledTaskRunStatus,
+        admin::provenance::VerifyProvenanceRequest,
+        admin::provenance::VerifyProvenanceResponse,
+        admin::provenance::ProvenanceMetadataInput,
+        crate::services::provenance::ProvenanceMetadata,
+        admin::org_request_policies::OrgRequestPolicyListResponse,
+        admin::org_request_policies::SimulateRequestPolicySubject,
+        admin::org_request_policies::SimulateRequestPoliciesRequest,
+        admin::org_request_policies::SimulateRequestPoliciesResponse,
         // Domain Verification types
         models::DomainVerification,
         models::CreateDomainVerification,
@@ -1156,6 +1603,8 @@ requests_per_minute = 120
         api::FileBatch,
         api::FileBatchCounts,
         api::CreateFileBatchRequest,
+        api::VectorStoreFileContentPart,
+        api::VectorStoreFileContentResponse,
         // Vector Store Chunks & Search (Hadrian extensions)
         api::ChunkResponse,
         api::ChunkListResponse,
@@ -1179,6 +1628,13 @@ requests_per_minute = 120
         api::WebSearchResult,
         api::WebFetchRequest,
         api::WebFetchResponse,
+        // Token counting types (Hadrian extension)
+        api::TokenizeRequest,
+        api::TokenizeResponse,
+        api::EstimateRequest,
+        api::EstimateResponse,
+        api::EstimateLimits,
+        api::LimitCheck,
         // Error response
         ErrorResponse,
         ErrorInfo,
@@ -1188,6 +1644,10 @@ requests_per_minute = 120
         health::HealthStatus,
         health::SubsystemStatus,
         health::ComponentStatus,
+        health::ReadinessStatus,
+        health::DependencyStatus,
+        health::DependencyKind,
+        health::DependencyState,
     )),
     security(
         ("api_key" = [])
@@ -1263,6 +1723,12 @@ pub struct ErrorInfo {
     )]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub request_id: Option<String>,
+    /// **Hadrian Extension:** Error category from the gateway's error taxonomy
+    /// (e.g. `rate_limited`, `context_length`). Filled in by the gateway
+    /// middleware when the handler didn't set it.
+    #[cfg_attr(feature = "utoipa", schema(example = "budget_exceeded"))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
 }
 
 impl ErrorResponse {
@@ -1278,6 +1744,7 @@ impl ErrorResponse {
                 param: None,
                 code: Some(code.into()),
                 request_id: None,
+                category: None,
             },
         }
     }
@@ -1298,6 +1765,7 @@ impl ErrorResponse {
                 param: Some(param.into()),
                 code: Some(code.into()),
                 request_id: None,
+                category: None,
             },
         }
     }
@@ -1325,6 +1793,7 @@ impl ErrorResponse {
                 param: None,
                 code: Some(code.into()),
                 request_id: None,
+                category: None,
             },
         }
     }
@@ -1424,7 +1893,7 @@ impl utoipa::Modify for SecurityAddon {
             },
             {
                 "name": "Admin API",
-                "tags": ["organizations", "projects", "teams", "users", "api-keys", "dynamic-providers", "usage", "model-pricing", "conversations", "dlq", "audit-logs", "access-reviews", "sso", "files", "vector-stores"]
+                "tags": ["organizations", "projects", "teams", "users", "api-keys", "dynamic-providers", "usage", "model-catalog", "model-pricing", "conversations", "dlq", "audit-logs", "reports", "federation", "reconciliation", "observability", "access-reviews", "sso", "files", "vector-stores"]
             }
         ]);
 
@@ -241,9 +241,13 @@ impl ModelPricing {
 /// Token usage breakdown for cost calculation
 #[derive(Debug, Clone, Default)]
 pub struct TokenUsage {
+    /// All prompt tokens, including cache reads and writes
     pub input_tokens: i64,
     pub output_tokens: i64,
+    /// Prompt tokens read from the provider's prompt cache
     pub cached_tokens: Option<i64>,
+    /// Prompt tokens written to the provider's prompt cache
+    pub cache_write_tokens: Option<i64>,
     pub reasoning_tokens: Option<i64>,
     pub image_count: Option<i64>,
     /// Image size string (e.g. `"1024x1024"`) for size-aware pricing
@@ -349,7 +353,9 @@ impl PricingConfig {
     /// the pricing data came from.
     ///
     /// First checks the pre-populated pricing HashMap, then falls back to
-    /// a runtime catalog lookup for models not in `allowed_models`.
+    /// a runtime catalog lookup for models not in `allowed_models`. Catalog
+    /// pricing pre-populated at startup is looked up again, so catalog syncs
+    /// and custom catalog entries take effect without a restart.
     pub fn calculate_cost_detailed(
         &self,
         provider: &str,
@@ -358,6 +364,11 @@ impl PricingConfig {
     ) -> Option<(i64, CostPricingSource)> {
         if let Some(pricing) = self.get(provider, model) {
             let source = self.get_source(provider, model);
+            if source == CostPricingSource::Catalog
+                && let Some(live) = self.lookup_catalog(provider, model)
+            {
+                return Some((Self::compute_cost(&live, usage), source));
+            }
             return Some((Self::compute_cost(pricing, usage), source));
         }
         if let Some(pricing) = self.lookup_catalog(provider, model) {
@@ -384,11 +395,14 @@ impl PricingConfig {
     fn compute_cost(pricing: &ModelPricing, usage: &TokenUsage) -> i64 {
         let mut total_microcents: i128 = 0;
 
-        // Input tokens (subtract cached if applicable)
+        // Input tokens, less those read from or written to the prompt cache
+        let cached = usage.cached_tokens.unwrap_or(0);
+        let cache_write = usage.cache_write_tokens.unwrap_or(0);
         let regular_input = usage
-            .cached_tokens
-            .map(|c| usage.input_tokens.saturating_sub(c))
-            .unwrap_or(usage.input_tokens);
+            .input_tokens
+            .saturating_sub(cached)
+            .saturating_sub(cache_write)
+            .max(0);
         total_microcents +=
             (regular_input as i128 * pricing.input_per_1m_tokens as i128) / 1_000_000;
 
@@ -396,12 +410,16 @@ impl PricingConfig {
         total_microcents +=
             (usage.output_tokens as i128 * pricing.output_per_1m_tokens as i128) / 1_000_000;
 
-        // Cached input tokens (if pricing available)
-        if let (Some(cached), Some(cached_price)) =
-            (usage.cached_tokens, pricing.cached_input_per_1m_tokens)
-        {
-            total_microcents += (cached as i128 * cached_price as i128) / 1_000_000;
-        }
+        // Cache reads and writes, at the regular input rate when the model
+        // has no separate cache pricing
+        let cached_price = pricing
+            .cached_input_per_1m_tokens
+            .unwrap_or(pricing.input_per_1m_tokens);
+        total_microcents += (cached as i128 * cached_price as i128) / 1_000_000;
+        let cache_write_price = pricing
+            .cache_write_per_1m_tokens
+            .unwrap_or(pricing.input_per_1m_tokens);
+        total_microcents += (cache_write as i128 * cache_write_price as i128) / 1_000_000;
 
         // Reasoning tokens
         if let (Some(reasoning), Some(reasoning_price)) =
@@ -787,6 +805,49 @@ mod tests {
         assert_eq!(cost.map(|(c, _)| c), Some(1_550_000));
     }
 
+    #[test]
+    fn test_detailed_usage_with_cache_writes() {
+        let mut config = PricingConfig::default();
+
+        // $1 per 1M input, $0.10 per 1M cached, $1.25 per 1M cache writes
+        config.set_pricing(
+            "test",
+            "cached-model",
+            ModelPricing {
+                input_per_1m_tokens: 1_000_000,
+                output_per_1m_tokens: 2_000_000,
+                cached_input_per_1m_tokens: Some(100_000),
+                cache_write_per_1m_tokens: Some(1_250_000),
+                ..Default::default()
+            },
+        );
+        // No separate cache pricing: cache tokens cost the regular input rate
+        config.set_pricing(
+            "test",
+            "uncached-model",
+            ModelPricing {
+                input_per_1m_tokens: 1_000_000,
+                output_per_1m_tokens: 2_000_000,
+                ..Default::default()
+            },
+        );
+
+        // 1M prompt tokens: 200k uncached, 500k read, 300k written
+        let usage = TokenUsage {
+            input_tokens: 1_000_000,
+            cached_tokens: Some(500_000),
+            cache_write_tokens: Some(300_000),
+            ..Default::default()
+        };
+
+        // 200_000 + 50_000 + 375_000
+        let cost = config.calculate_cost_detailed("test", "cached-model", &usage);
+        assert_eq!(cost.map(|(c, _)| c), Some(625_000));
+
+        let cost = config.calculate_cost_detailed("test", "uncached-model", &usage);
+        assert_eq!(cost.map(|(c, _)| c), Some(1_000_000));
+    }
+
     #[test]
     fn test_merge_configs() {
         let mut base = PricingConfig::default();
@@ -990,6 +1051,7 @@ mod tests {
             output_tokens: 10_000_000_000,
             reasoning_tokens: Some(10_000_000_000),
             cached_tokens: Some(5_000_000_000),
+            cache_write_tokens: None,
             image_count: Some(1_000_000),
             image_size: None,
             image_quality: None,
@@ -1412,6 +1474,61 @@ mod tests {
         assert_eq!(cost.map(|(c, _)| c), Some(12_500_000));
     }
 
+    #[test]
+    fn test_custom_catalog_entry_pricing() {
+        use chrono::Utc;
+        use uuid::Uuid;
+
+        use crate::{
+            catalog::{CatalogPricing, ModelCatalogRegistry},
+            config::ProvidersConfig,
+            models::{CatalogEntryMetadata, ModelCatalogEntry},
+        };
+
+        let catalog = ModelCatalogRegistry::new();
+        let providers: ProvidersConfig = toml::from_str(
+            r#"
+            [local]
+            type = "open_ai"
+            base_url = "http://vllm.internal:8000/v1"
+            catalog_provider = "vllm"
+            allowed_models = ["llama-ft"]
+            "#,
+        )
+        .unwrap();
+        let result = PricingConfig::from_config_with_catalog(
+            &PricingConfig::default(),
+            &providers,
+            Some(&catalog),
+        );
+        assert!(
+            result
+                .calculate_cost("local", "llama-ft", 1_000_000, 0)
+                .is_none()
+        );
+
+        // Entries added after startup are picked up at calculation time
+        catalog.set_custom_entries(&[ModelCatalogEntry {
+            id: Uuid::new_v4(),
+            provider: "vllm".to_string(),
+            model: "llama-ft".to_string(),
+            metadata: CatalogEntryMetadata {
+                pricing: Some(CatalogPricing {
+                    input: 0.2,
+                    output: 0.4,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }]);
+        assert_eq!(
+            result.calculate_cost("local", "llama-ft", 1_000_000, 1_000_000),
+            Some((600_000, CostPricingSource::Catalog))
+        );
+    }
+
     #[test]
     fn test_explicit_pricing_overrides_catalog() {
         use crate::{catalog::ModelCatalogRegistry, config::ProvidersConfig};
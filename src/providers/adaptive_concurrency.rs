@@ -0,0 +1,499 @@
+//! Adaptive concurrency limits for providers.
+//!
+//! Each provider with an `adaptive_concurrency` block caps its requests in
+//! flight at a limit that follows the upstream's behaviour (AIMD):
+//!
+//! - **Additive increase**: each successful request adds `1 / limit`, so the
+//!   limit grows by about one per round of requests. It only grows while at
+//!   least half of it is in use, so an idle provider doesn't accumulate a
+//!   limit it has never been tested at.
+//! - **Multiplicative decrease**: a 429, 503 or timeout, or a latency above
+//!   `latency_tolerance` times the baseline, multiplies the limit by
+//!   `backoff_ratio`. Requests that were sent before the last decrease don't
+//!   trigger another one, so a burst of failures from one overload only backs
+//!   off once.
+//!
+//! The baseline is the lowest latency seen, drifting slowly upwards so it
+//! follows a provider that has become permanently slower. Latency is measured
+//! to the response headers: for streaming requests that is time to first
+//! byte, which doesn't depend on how long the answer is.
+
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use axum::response::Response;
+use http::StatusCode;
+use serde::Serialize;
+use thiserror::Error;
+use tokio::sync::Notify;
+
+use super::ProviderError;
+use crate::{
+    compat::{Mutex, RwLock},
+    config::{AdaptiveConcurrencyConfig, ProvidersConfig},
+    observability::metrics,
+};
+
+/// Weight of a new sample in the recent latency average.
+const RECENT_LATENCY_WEIGHT: f64 = 0.2;
+
+/// How far the baseline moves towards a slower sample.
+const BASELINE_DRIFT: f64 = 0.01;
+
+/// Error returned when a request can't get a slot within the limit.
+#[derive(Debug, Error)]
+#[error(
+    "Provider '{provider}' is at its concurrency limit ({limit}) and the request waited {waited_ms}ms without getting a slot"
+)]
+pub struct ConcurrencyLimitError {
+    provider: Arc<str>,
+    limit: u32,
+    waited_ms: u64,
+}
+
+/// What a finished request says about the provider's load.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadSignal {
+    /// The provider answered normally after this long.
+    Latency(Duration),
+    /// The provider is shedding load (429, 503, timeout).
+    Overloaded(&'static str),
+    /// Tells us nothing about load (e.g. a 400 or a local error).
+    Neutral,
+}
+
+impl LoadSignal {
+    /// Classify the result of a provider call that took `elapsed`.
+    pub fn from_result(result: &Result<Response, ProviderError>, elapsed: Duration) -> Self {
+        match result {
+            Ok(response) => match response.status() {
+                StatusCode::TOO_MANY_REQUESTS => Self::Overloaded("rate_limited"),
+                StatusCode::SERVICE_UNAVAILABLE => Self::Overloaded("unavailable"),
+                StatusCode::GATEWAY_TIMEOUT => Self::Overloaded("timeout"),
+                status if status.is_success() => Self::Latency(elapsed),
+                _ => Self::Neutral,
+            },
+            Err(ProviderError::Request(e)) if e.is_timeout() => Self::Overloaded("timeout"),
+            Err(_) => Self::Neutral,
+        }
+    }
+}
+
+struct LimiterState {
+    limit: f64,
+    in_flight: u32,
+    waiting: usize,
+    baseline: Option<Duration>,
+    recent: Option<Duration>,
+    last_decrease: Option<Instant>,
+}
+
+/// Current state of a provider's adaptive limit.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ConcurrencyStatus {
+    /// Provider name.
+    pub provider: String,
+    /// Requests currently allowed in flight.
+    pub limit: u32,
+    pub min_limit: u32,
+    pub max_limit: u32,
+    /// Requests currently in flight.
+    pub in_flight: u32,
+    /// Requests waiting for a slot.
+    pub waiting: usize,
+    /// Lowest recent latency to response headers, in milliseconds.
+    pub baseline_latency_ms: Option<u64>,
+    /// Moving average of latency to response headers, in milliseconds.
+    pub recent_latency_ms: Option<u64>,
+    /// Seconds since the limit was last cut, if it has been.
+    pub last_decrease_secs_ago: Option<u64>,
+}
+
+/// Adaptive concurrency limiter for one provider.
+pub struct AdaptiveLimiter {
+    provider: Arc<str>,
+    config: AdaptiveConcurrencyConfig,
+    state: Mutex<LimiterState>,
+    released: Notify,
+}
+
+impl AdaptiveLimiter {
+    pub fn new(provider: &str, config: &AdaptiveConcurrencyConfig) -> Self {
+        metrics::set_provider_concurrency_limit(provider, config.initial_limit);
+        Self {
+            provider: Arc::from(provider),
+            config: config.clone(),
+            state: Mutex::new(LimiterState {
+                limit: f64::from(config.initial_limit),
+                in_flight: 0,
+                waiting: 0,
+                baseline: None,
+                recent: None,
+                last_decrease: None,
+            }),
+            released: Notify::new(),
+        }
+    }
+
+    /// Wait for a slot within the current limit.
+    pub async fn acquire(self: &Arc<Self>) -> Result<ConcurrencyPermit, ConcurrencyLimitError> {
+        let started = Instant::now();
+        let deadline = started + Duration::from_millis(self.config.queue_timeout_ms);
+        let mut guard: Option<WaitGuard<'_>> = None;
+
+        let result = loop {
+            // Register for a wakeup before checking, so a release between the
+            // check and the wait isn't missed.
+            let notified = self.released.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            let limit = {
+                let mut state = self.state.lock();
+                if state.in_flight < state.limit as u32 {
+                    state.in_flight += 1;
+                    if let Some(guard) = guard.as_mut() {
+                        guard.acquired = true;
+                    }
+                    break Ok(());
+                }
+                if guard.is_none() {
+                    state.waiting += 1;
+                    guard = Some(WaitGuard {
+                        limiter: self,
+                        acquired: false,
+                    });
+                }
+                state.limit as u32
+            };
+
+            let now = Instant::now();
+            if now >= deadline
+                || tokio::time::timeout(deadline - now, notified)
+                    .await
+                    .is_err()
+            {
+                break Err(limit);
+            }
+        };
+        drop(guard);
+
+        match result {
+            Ok(()) => Ok(ConcurrencyPermit {
+                limiter: Arc::clone(self),
+                sent_at: Instant::now(),
+            }),
+            Err(limit) => {
+                let waited_ms = started.elapsed().as_millis() as u64;
+                tracing::warn!(
+                    provider = %self.provider,
+                    limit,
+                    waited_ms,
+                    "Timed out waiting for a slot under the adaptive concurrency limit"
+                );
+                metrics::record_provider_concurrency_rejected(&self.provider);
+                Err(ConcurrencyLimitError {
+                    provider: self.provider.clone(),
+                    limit,
+                    waited_ms,
+                })
+            }
+        }
+    }
+
+    /// Adjust the limit for a request sent at `sent_at`.
+    fn record(&self, sent_at: Instant, signal: LoadSignal) {
+        let mut state = self.state.lock();
+        let reason = match signal {
+            LoadSignal::Neutral => return,
+            LoadSignal::Overloaded(reason) => Some(reason),
+            LoadSignal::Latency(latency) => {
+                let recent = match state.recent {
+                    Some(recent) => {
+                        recent.mul_f64(1.0 - RECENT_LATENCY_WEIGHT)
+                            + latency.mul_f64(RECENT_LATENCY_WEIGHT)
+                    }
+                    None => latency,
+                };
+                let baseline = match state.baseline {
+                    Some(baseline) if latency > baseline => {
+                        baseline + (latency - baseline).mul_f64(BASELINE_DRIFT)
+                    }
+                    _ => latency,
+                };
+                state.recent = Some(recent);
+                state.baseline = Some(baseline);
+                (recent > baseline.mul_f64(self.config.latency_tolerance)).then_some("latency")
+            }
+        };
+
+        let previous = state.limit as u32;
+        match reason {
+            Some(reason) => {
+                // Requests sent before the last cut reflect the old limit
+                if state.last_decrease.is_some_and(|at| sent_at < at) {
+                    return;
+                }
+                state.limit =
+                    (state.limit * self.config.backoff_ratio).max(f64::from(self.config.min_limit));
+                state.last_decrease = Some(Instant::now());
+                metrics::record_provider_concurrency_backoff(&self.provider, reason);
+                tracing::debug!(
+                    provider = %self.provider,
+                    reason,
+                    limit = state.limit as u32,
+                    "Reduced adaptive concurrency limit"
+                );
+            }
+            None => {
+                if f64::from(state.in_flight) < state.limit / 2.0 {
+                    return;
+                }
+                state.limit =
+                    (state.limit + 1.0 / state.limit).min(f64::from(self.config.max_limit));
+            }
+        }
+
+        let limit = state.limit as u32;
+        drop(state);
+        if limit != previous {
+            metrics::set_provider_concurrency_limit(&self.provider, limit);
+        }
+        if limit > previous {
+            self.released.notify_one();
+        }
+    }
+
+    fn release(&self) {
+        {
+            let mut state = self.state.lock();
+            state.in_flight = state.in_flight.saturating_sub(1);
+        }
+        self.released.notify_one();
+    }
+
+    /// Snapshot of the limiter for monitoring.
+    pub fn status(&self) -> ConcurrencyStatus {
+        let state = self.state.lock();
+        ConcurrencyStatus {
+            provider: self.provider.to_string(),
+            limit: state.limit as u32,
+            min_limit: self.config.min_limit,
+            max_limit: self.config.max_limit,
+            in_flight: state.in_flight,
+            waiting: state.waiting,
+            baseline_latency_ms: state.baseline.map(|d| d.as_millis() as u64),
+            recent_latency_ms: state.recent.map(|d| d.as_millis() as u64),
+            last_decrease_secs_ago: state.last_decrease.map(|at| at.elapsed().as_secs()),
+        }
+    }
+}
+
+/// Counts a request as waiting until it gets a slot, times out or is
+/// dropped (e.g. the client disconnected).
+struct WaitGuard<'a> {
+    limiter: &'a AdaptiveLimiter,
+    acquired: bool,
+}
+
+impl Drop for WaitGuard<'_> {
+    fn drop(&mut self) {
+        self.limiter.state.lock().waiting -= 1;
+        if !self.acquired {
+            // The wakeup this waiter may have consumed belongs to someone else
+            self.limiter.released.notify_one();
+        }
+    }
+}
+
+/// A slot under a provider's adaptive limit. Dropping it frees the slot.
+pub struct ConcurrencyPermit {
+    limiter: Arc<AdaptiveLimiter>,
+    sent_at: Instant,
+}
+
+impl ConcurrencyPermit {
+    /// Feed the outcome of the request into the limit.
+    pub fn record(&self, signal: LoadSignal) {
+        self.limiter.record(self.sent_at, signal);
+    }
+
+    /// When the request was sent.
+    pub fn sent_at(&self) -> Instant {
+        self.sent_at
+    }
+}
+
+impl Drop for ConcurrencyPermit {
+    fn drop(&mut self) {
+        self.limiter.release();
+    }
+}
+
+/// Registry of adaptive limiters keyed by provider name.
+#[derive(Clone, Default)]
+pub struct AdaptiveConcurrencyRegistry {
+    limiters: Arc<RwLock<HashMap<String, Arc<AdaptiveLimiter>>>>,
+}
+
+impl AdaptiveConcurrencyRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry with a limiter for every provider that configures one.
+    pub fn from_config(providers: &ProvidersConfig) -> Self {
+        let registry = Self::new();
+        {
+            let mut limiters = registry.limiters.write();
+            for (name, config) in providers.iter() {
+                if let Some(adaptive) = config.adaptive_concurrency_config() {
+                    limiters.insert(
+                        name.to_string(),
+                        Arc::new(AdaptiveLimiter::new(name, adaptive)),
+                    );
+                }
+            }
+        }
+        registry
+    }
+
+    /// Get the limiter for a provider, creating it if the provider configures
+    /// one but wasn't known at startup (e.g. dynamic providers).
+    pub fn get_or_create(
+        &self,
+        provider_name: &str,
+        config: Option<&AdaptiveConcurrencyConfig>,
+    ) -> Option<Arc<AdaptiveLimiter>> {
+        let config = config?;
+        if let Some(limiter) = self.limiters.read().get(provider_name) {
+            return Some(limiter.clone());
+        }
+        let mut limiters = self.limiters.write();
+        Some(
+            limiters
+                .entry(provider_name.to_string())
+                .or_insert_with(|| Arc::new(AdaptiveLimiter::new(provider_name, config)))
+                .clone(),
+        )
+    }
+
+    /// Status of the limiter for a provider, if it has one.
+    pub fn status_for(&self, provider_name: &str) -> Option<ConcurrencyStatus> {
+        self.limiters
+            .read()
+            .get(provider_name)
+            .map(|limiter| limiter.status())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter(initial_limit: u32) -> Arc<AdaptiveLimiter> {
+        Arc::new(AdaptiveLimiter::new(
+            "p",
+            &AdaptiveConcurrencyConfig {
+                initial_limit,
+                min_limit: 1,
+                max_limit: 10,
+                queue_timeout_ms: 20,
+                ..Default::default()
+            },
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_limit_caps_in_flight() {
+        let limiter = limiter(2);
+        let a = limiter.acquire().await.unwrap();
+        let _b = limiter.acquire().await.unwrap();
+        assert!(limiter.acquire().await.is_err());
+        assert_eq!(limiter.status().waiting, 0);
+
+        drop(a);
+        let _c = limiter.acquire().await.unwrap();
+        assert_eq!(limiter.status().in_flight, 2);
+    }
+
+    #[tokio::test]
+    async fn test_waiter_gets_released_slot() {
+        let limiter = limiter(1);
+        let held = limiter.acquire().await.unwrap();
+        let waiting = tokio::spawn({
+            let limiter = limiter.clone();
+            async move { limiter.acquire().await.map(|_| ()) }
+        });
+        tokio::task::yield_now().await;
+        drop(held);
+        assert!(waiting.await.unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_overload_backs_off_once_per_round() {
+        let limiter = limiter(10);
+        let permits: Vec<_> = futures_util::future::join_all((0..3).map(|_| limiter.acquire()))
+            .await
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+
+        // Three requests from the same round all see a 429: one cut
+        for permit in &permits {
+            permit.record(LoadSignal::Overloaded("rate_limited"));
+        }
+        assert_eq!(limiter.status().limit, 9);
+        drop(permits);
+
+        // A request sent after the cut can cut again
+        let permit = limiter.acquire().await.unwrap();
+        permit.record(LoadSignal::Overloaded("rate_limited"));
+        assert_eq!(limiter.status().limit, 8);
+    }
+
+    #[tokio::test]
+    async fn test_success_grows_limit_only_when_busy() {
+        let fast = LoadSignal::Latency(Duration::from_millis(100));
+
+        // Both slots in use: each success adds 1/limit
+        let limiter = limiter(2);
+        let a = limiter.acquire().await.unwrap();
+        let b = limiter.acquire().await.unwrap();
+        for _ in 0..2 {
+            a.record(fast);
+            b.record(fast);
+        }
+        assert_eq!(limiter.status().limit, 3);
+
+        // Never more than one of eight slots in use: no growth
+        let limiter = self::limiter(8);
+        for _ in 0..20 {
+            let permit = limiter.acquire().await.unwrap();
+            permit.record(fast);
+        }
+        assert_eq!(limiter.status().limit, 8);
+    }
+
+    #[tokio::test]
+    async fn test_rising_latency_backs_off() {
+        let limiter = limiter(10);
+        let permit = limiter.acquire().await.unwrap();
+        permit.record(LoadSignal::Latency(Duration::from_millis(100)));
+        drop(permit);
+
+        for _ in 0..10 {
+            let permit = limiter.acquire().await.unwrap();
+            permit.record(LoadSignal::Latency(Duration::from_secs(2)));
+        }
+        let status = limiter.status();
+        assert!(status.limit < 10);
+        // The baseline drifts towards the slower latency but stays well below it
+        assert!(status.baseline_latency_ms.unwrap() < 500);
+    }
+}
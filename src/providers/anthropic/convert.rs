@@ -4,10 +4,10 @@
 
 use super::types::{
     AnthropicCacheControl, AnthropicCacheControlType, AnthropicContent, AnthropicEffort,
-    AnthropicMessage, AnthropicOutputConfig, AnthropicResponse, AnthropicThinkingConfig,
-    AnthropicThinkingDisplay, AnthropicTool, AnthropicToolChoice, ContentBlock, ImageSource,
-    OpenAIChoice, OpenAIMessage, OpenAIResponse, OpenAIToolCall, OpenAIToolCallFunction,
-    OpenAIUsage, PromptTokensDetails,
+    AnthropicMessage, AnthropicOutputConfig, AnthropicRequest, AnthropicResponse, AnthropicSystem,
+    AnthropicThinkingConfig, AnthropicThinkingDisplay, AnthropicTool, AnthropicToolChoice,
+    ContentBlock, ImageSource, OpenAIChoice, OpenAIMessage, OpenAIResponse, OpenAIToolCall,
+    OpenAIToolCallFunction, OpenAIUsage, PromptTokensDetails,
 };
 use crate::{
     api_types::{
@@ -27,6 +27,7 @@ use crate::{
             ResponsesUsageInputTokensDetails, ResponsesUsageOutputTokensDetails,
         },
     },
+    config::PromptCachingConfig,
     providers::{
         convert_utils::{easy_content_text, input_content_text},
         image::parse_data_url,
@@ -335,6 +336,56 @@ pub fn convert_messages(
     (system_prompt, messages)
 }
 
+/// Cache breakpoint the caller placed on a system/developer message that
+/// [`convert_messages`] folds into the top-level `system` prompt.
+///
+/// The folded prompt is a single block, so a breakpoint on any of its parts
+/// marks the whole prompt.
+pub fn system_cache_control(
+    messages: &[Message],
+    mid_conversation_system: bool,
+) -> Option<AnthropicCacheControl> {
+    let mut leading = true;
+    for msg in messages {
+        match msg {
+            Message::System { content, .. } | Message::Developer { content, .. } => {
+                if !leading && mid_conversation_system {
+                    continue;
+                }
+                if let MessageContent::Parts(parts) = content {
+                    let cache_control = parts.iter().find_map(|part| match part {
+                        ContentPart::Text { cache_control, .. } => cache_control.as_ref(),
+                        _ => None,
+                    });
+                    if cache_control.is_some() {
+                        return convert_cache_control(cache_control);
+                    }
+                }
+            }
+            _ => leading = false,
+        }
+    }
+    None
+}
+
+/// Mark the system prompt as a cache breakpoint when `prompt_caching` asks
+/// for it and the caller hasn't placed any breakpoints of their own.
+pub fn apply_prompt_caching(request: &mut AnthropicRequest, prompt_caching: &PromptCachingConfig) {
+    if request.has_cache_breakpoint() {
+        return;
+    }
+    if let Some(AnthropicSystem::Text(text)) = &mut request.system
+        && prompt_caching.caches_system_prompt(text)
+    {
+        request.system = Some(AnthropicSystem::new(
+            std::mem::take(text),
+            Some(AnthropicCacheControl {
+                type_: AnthropicCacheControlType::Ephemeral,
+            }),
+        ));
+    }
+}
+
 /// Convert stop sequences from OpenAI format
 pub fn convert_stop(stop: Option<Stop>) -> Option<Vec<String>> {
     stop.map(|s| match s {
@@ -419,9 +470,9 @@ pub fn convert_response(anthropic: AnthropicResponse) -> OpenAIResponse {
             logprobs: None,
         }],
         usage: Some(OpenAIUsage {
-            prompt_tokens: anthropic.usage.input_tokens,
+            prompt_tokens: anthropic.usage.prompt_tokens(),
             completion_tokens: anthropic.usage.output_tokens,
-            total_tokens: anthropic.usage.input_tokens + anthropic.usage.output_tokens,
+            total_tokens: anthropic.usage.prompt_tokens() + anthropic.usage.output_tokens,
             prompt_tokens_details: if anthropic.usage.cache_read_input_tokens > 0
                 || anthropic.usage.cache_creation_input_tokens > 0
             {
@@ -1258,15 +1309,16 @@ pub fn convert_anthropic_to_responses_response(
         error: None,
         incomplete_details: None,
         usage: Some(ResponsesUsage {
-            input_tokens: anthropic.usage.input_tokens,
+            input_tokens: anthropic.usage.prompt_tokens(),
             input_tokens_details: ResponsesUsageInputTokensDetails {
                 cached_tokens: anthropic.usage.cache_read_input_tokens,
+                cache_creation_input_tokens: anthropic.usage.cache_creation_input_tokens,
             },
             output_tokens: anthropic.usage.output_tokens,
             output_tokens_details: ResponsesUsageOutputTokensDetails {
                 reasoning_tokens: 0, // Anthropic doesn't report this separately
             },
-            total_tokens: anthropic.usage.input_tokens + anthropic.usage.output_tokens,
+            total_tokens: anthropic.usage.prompt_tokens() + anthropic.usage.output_tokens,
             cost: None,
             is_byok: None,
             cost_details: None,
@@ -1387,6 +1439,98 @@ mod tests {
         assert_eq!(anthropic_msgs[0].role, "user");
     }
 
+    #[test]
+    fn test_system_prompt_cache_control() {
+        use crate::api_types::chat_completion::CacheControlType;
+
+        let messages = vec![
+            Message::System {
+                content: MessageContent::Parts(vec![ContentPart::Text {
+                    text: "You are helpful".to_string(),
+                    cache_control: Some(CacheControl {
+                        type_: CacheControlType::Ephemeral,
+                    }),
+                }]),
+                name: None,
+            },
+            Message::User {
+                content: MessageContent::Text("Hello".to_string()),
+                name: None,
+            },
+        ];
+        let cache_control = system_cache_control(&messages, false);
+        assert!(cache_control.is_some());
+
+        let (system, _) = convert_messages(messages, false);
+        let system = AnthropicSystem::new(system.unwrap(), cache_control);
+        assert_eq!(
+            serde_json::to_value(&system).unwrap(),
+            serde_json::json!([{
+                "type": "text",
+                "text": "You are helpful",
+                "cache_control": {"type": "ephemeral"}
+            }])
+        );
+
+        // Without a breakpoint the prompt stays a plain string
+        let system = AnthropicSystem::new("You are helpful".to_string(), None);
+        assert_eq!(
+            serde_json::to_value(&system).unwrap(),
+            serde_json::json!("You are helpful")
+        );
+    }
+
+    #[test]
+    fn test_apply_prompt_caching() {
+        let request = |system: &str| AnthropicRequest {
+            model: "claude-sonnet-4-5".to_string(),
+            messages: vec![AnthropicMessage {
+                role: "user".to_string(),
+                content: AnthropicContent::Text("Hello".to_string()),
+            }],
+            max_tokens: 1024,
+            system: Some(AnthropicSystem::Text(system.to_string())),
+            temperature: None,
+            top_p: None,
+            top_k: None,
+            stop_sequences: None,
+            stream: false,
+            tools: None,
+            tool_choice: None,
+            thinking: None,
+            output_config: None,
+            metadata: None,
+        };
+        let prompt_caching = PromptCachingConfig {
+            auto_cache_system_prompt: true,
+            min_system_prompt_chars: 10,
+        };
+
+        let mut long = request("You are a helpful assistant.");
+        apply_prompt_caching(&mut long, &prompt_caching);
+        assert!(long.has_cache_breakpoint());
+        assert!(matches!(long.system, Some(AnthropicSystem::Blocks(_))));
+
+        let mut short = request("Be terse.");
+        apply_prompt_caching(&mut short, &prompt_caching);
+        assert!(!short.has_cache_breakpoint());
+
+        let mut disabled = request("You are a helpful assistant.");
+        apply_prompt_caching(&mut disabled, &PromptCachingConfig::default());
+        assert!(!disabled.has_cache_breakpoint());
+
+        // Caller-placed breakpoints are left alone
+        let mut explicit = request("You are a helpful assistant.");
+        explicit.messages[0].content = AnthropicContent::Blocks(vec![ContentBlock::Text {
+            text: "Hello".to_string(),
+            cache_control: Some(AnthropicCacheControl {
+                type_: AnthropicCacheControlType::Ephemeral,
+            }),
+        }]);
+        apply_prompt_caching(&mut explicit, &prompt_caching);
+        assert!(matches!(explicit.system, Some(AnthropicSystem::Text(_))));
+    }
+
     #[test]
     fn test_convert_messages_multiple_system_messages() {
         // Multiple system messages should be concatenated with double newlines
@@ -2677,14 +2821,16 @@ mod tests {
 
         let openai_response = convert_response(anthropic_response);
 
+        // Prompt tokens include cache reads and writes
         let usage = openai_response.usage.unwrap();
-        assert_eq!(usage.prompt_tokens, 100);
+        assert_eq!(usage.prompt_tokens, 135);
         assert_eq!(usage.completion_tokens, 50);
-        assert_eq!(usage.total_tokens, 150);
+        assert_eq!(usage.total_tokens, 185);
 
         // Should have cache tokens
         let details = usage.prompt_tokens_details.unwrap();
         assert_eq!(details.cached_tokens, 25);
+        assert_eq!(details.cache_creation_input_tokens, 10);
     }
 
     #[test]
@@ -12,22 +12,28 @@ use std::{sync::Arc, time::Duration};
 use async_trait::async_trait;
 use axum::response::Response;
 use convert::{
-    adaptive_thinking_config, convert_anthropic_to_responses_response,
+    adaptive_thinking_config, apply_prompt_caching, convert_anthropic_to_responses_response,
     convert_chat_completion_reasoning_config, convert_messages, convert_reasoning_config,
     convert_response, convert_responses_input_to_messages, convert_responses_tool_choice,
     convert_responses_tools, convert_stop, convert_tool_choice, convert_tools,
-    requires_strict_thinking, supports_mid_conversation_system,
+    requires_strict_thinking, supports_mid_conversation_system, system_cache_control,
 };
 use serde::Deserialize;
 use stream::{AnthropicToOpenAIStream, AnthropicToResponsesStream};
-use types::{AnthropicMetadata, AnthropicRequest, AnthropicResponse};
+use types::{
+    AnthropicCountTokensRequest, AnthropicCountTokensResponse, AnthropicMetadata, AnthropicRequest,
+    AnthropicResponse, AnthropicSystem,
+};
 
 use crate::{
     api_types::{
         CreateChatCompletionPayload, CreateCompletionPayload, CreateEmbeddingPayload,
         CreateResponsesPayload,
     },
-    config::{AnthropicProviderConfig, CircuitBreakerConfig, RetryConfig, StreamingBufferConfig},
+    config::{
+        AnthropicProviderConfig, CircuitBreakerConfig, PromptCachingConfig, RetryConfig,
+        StreamingBufferConfig,
+    },
     providers::{
         CircuitBreakerRegistry, ModelInfo, ModelsResponse, Provider, ProviderError,
         circuit_breaker::CircuitBreaker,
@@ -41,6 +47,9 @@ use crate::{
 /// Anthropic API version header value.
 const ANTHROPIC_VERSION: &str = "2023-06-01";
 
+/// API version for Claude on Vertex AI, sent in the body instead of a header.
+const VERTEX_ANTHROPIC_VERSION: &str = "vertex-2023-10-16";
+
 /// Default max tokens if not specified.
 const DEFAULT_MAX_TOKENS: u32 = 4096;
 
@@ -214,6 +223,9 @@ fn apply_task_budget(
 pub struct AnthropicProvider {
     api_key: String,
     base_url: String,
+    /// Google access token when serving Claude on Vertex AI. `base_url` is
+    /// then the `anthropic` publisher's models URL.
+    vertex_token: Option<String>,
     default_model: Option<String>,
     default_max_tokens: Option<u32>,
     timeout: Duration,
@@ -226,6 +238,7 @@ pub struct AnthropicProvider {
     adaptive_thinking_models: Vec<String>,
     strict_thinking_models: Vec<String>,
     mid_conversation_system_models: Vec<String>,
+    prompt_caching: PromptCachingConfig,
 }
 
 impl AnthropicProvider {
@@ -260,6 +273,7 @@ impl AnthropicProvider {
         Self {
             api_key: config.api_key.clone(),
             base_url: config.base_url.trim_end_matches('/').to_string(),
+            vertex_token: None,
             default_model: config.default_model.clone(),
             default_max_tokens: config.default_max_tokens,
             timeout: Duration::from_secs(config.timeout_secs),
@@ -272,6 +286,78 @@ impl AnthropicProvider {
             adaptive_thinking_models: config.adaptive_thinking_models.clone(),
             strict_thinking_models: config.strict_thinking_models.clone(),
             mid_conversation_system_models: config.mid_conversation_system_models.clone(),
+            prompt_caching: config.prompt_caching.clone(),
+        }
+    }
+
+    /// Create a provider for Claude on Vertex AI. Requests go to
+    /// `{models_url}/{model}:rawPredict` with `access_token`.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn for_vertex(
+        models_url: String,
+        access_token: String,
+        timeout: Duration,
+        retry: RetryConfig,
+        circuit_breaker_config: CircuitBreakerConfig,
+        circuit_breaker: Option<Arc<CircuitBreaker>>,
+        streaming_buffer: StreamingBufferConfig,
+        mut image_fetch_config: ImageFetchConfig,
+    ) -> Self {
+        image_fetch_config.pass_through_https = true;
+        Self {
+            api_key: String::new(),
+            base_url: models_url,
+            vertex_token: Some(access_token),
+            default_model: None,
+            default_max_tokens: None,
+            timeout,
+            retry,
+            circuit_breaker_config,
+            circuit_breaker,
+            streaming_buffer,
+            image_fetch_config,
+            interleaved_thinking_models: crate::config::default_interleaved_thinking_models(),
+            adaptive_thinking_models: crate::config::default_adaptive_thinking_models(),
+            strict_thinking_models: crate::config::default_strict_thinking_models(),
+            mid_conversation_system_models: crate::config::default_mid_conversation_system_models(),
+            prompt_caching: PromptCachingConfig::default(),
+        }
+    }
+
+    /// URL and serialized body for a Messages API request. Vertex AI takes
+    /// the model in the URL and the API version in the body.
+    fn messages_call(&self, request: &AnthropicRequest) -> (String, Vec<u8>) {
+        if self.vertex_token.is_none() {
+            let body = serde_json::to_vec(request).unwrap_or_default();
+            return (format!("{}/v1/messages", self.base_url), body);
+        }
+
+        let method = if request.stream {
+            "streamRawPredict"
+        } else {
+            "rawPredict"
+        };
+        let mut body = serde_json::to_value(request).unwrap_or_default();
+        if let Some(fields) = body.as_object_mut() {
+            fields.remove("model");
+            fields.insert(
+                "anthropic_version".to_string(),
+                VERTEX_ANTHROPIC_VERSION.into(),
+            );
+        }
+        (
+            format!("{}/{}:{}", self.base_url, request.model, method),
+            serde_json::to_vec(&body).unwrap_or_default(),
+        )
+    }
+
+    /// Add authentication and version headers for a Messages API request.
+    fn authorize(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match &self.vertex_token {
+            Some(token) => request.bearer_auth(token),
+            None => request
+                .header("x-api-key", &self.api_key)
+                .header("anthropic-version", ANTHROPIC_VERSION),
         }
     }
 }
@@ -323,7 +409,10 @@ impl Provider for AnthropicProvider {
         // support them (Opus 4.8); otherwise they fold into the system prompt.
         let mid_conversation_system =
             supports_mid_conversation_system(&model, &self.mid_conversation_system_models);
+        let system_cache_control =
+            system_cache_control(&messages_to_convert, mid_conversation_system);
         let (system, messages) = convert_messages(messages_to_convert, mid_conversation_system);
+        let system = system.map(|text| AnthropicSystem::new(text, system_cache_control));
         let stream = payload.stream;
 
         // Convert tools and tool_choice
@@ -370,7 +459,7 @@ impl Provider for AnthropicProvider {
             user_id: Some(user_id),
         });
 
-        let anthropic_request = AnthropicRequest {
+        let mut anthropic_request = AnthropicRequest {
             model,
             messages,
             max_tokens,
@@ -386,6 +475,7 @@ impl Provider for AnthropicProvider {
             output_config,
             metadata,
         };
+        apply_prompt_caching(&mut anthropic_request, &self.prompt_caching);
 
         // Pre-serialize request body before retry loop to avoid repeated serialization
         let beta_header = compute_beta_header(
@@ -394,10 +484,7 @@ impl Provider for AnthropicProvider {
             &anthropic_request.output_config,
             &self.interleaved_thinking_models,
         );
-        let body = serde_json::to_vec(&anthropic_request).unwrap_or_default();
-
-        let url = format!("{}/v1/messages", self.base_url);
-        let api_key = self.api_key.clone();
+        let (url, body) = self.messages_call(&anthropic_request);
         let timeout = self.timeout;
 
         let response = with_circuit_breaker_and_retry(
@@ -407,10 +494,8 @@ impl Provider for AnthropicProvider {
             "anthropic",
             "chat_completion",
             || async {
-                let mut req = client
-                    .post(&url)
-                    .header("x-api-key", &api_key)
-                    .header("anthropic-version", ANTHROPIC_VERSION)
+                let mut req = self
+                    .authorize(client.post(&url))
                     .header("content-type", "application/json")
                     .timeout(timeout);
                 if let Some(beta) = &beta_header {
@@ -549,11 +634,11 @@ impl Provider for AnthropicProvider {
             user_id: Some(user_id),
         });
 
-        let anthropic_request = AnthropicRequest {
+        let mut anthropic_request = AnthropicRequest {
             model,
             messages,
             max_tokens,
-            system,
+            system: system.map(AnthropicSystem::Text),
             temperature,
             top_p,
             top_k: None,
@@ -565,6 +650,7 @@ impl Provider for AnthropicProvider {
             output_config,
             metadata,
         };
+        apply_prompt_caching(&mut anthropic_request, &self.prompt_caching);
 
         // Pre-serialize request body before retry loop to avoid repeated serialization
         let beta_header = compute_beta_header(
@@ -573,10 +659,7 @@ impl Provider for AnthropicProvider {
             &anthropic_request.output_config,
             &self.interleaved_thinking_models,
         );
-        let body = serde_json::to_vec(&anthropic_request).unwrap_or_default();
-
-        let url = format!("{}/v1/messages", self.base_url);
-        let api_key = self.api_key.clone();
+        let (url, body) = self.messages_call(&anthropic_request);
         let timeout = self.timeout;
 
         let response = with_circuit_breaker_and_retry(
@@ -586,10 +669,8 @@ impl Provider for AnthropicProvider {
             "anthropic",
             "responses",
             || async {
-                let mut req = client
-                    .post(&url)
-                    .header("x-api-key", &api_key)
-                    .header("anthropic-version", ANTHROPIC_VERSION)
+                let mut req = self
+                    .authorize(client.post(&url))
                     .header("content-type", "application/json")
                     .timeout(timeout);
                 if let Some(beta) = &beta_header {
@@ -724,6 +805,78 @@ impl Provider for AnthropicProvider {
 
         Ok(ModelsResponse { data: all_models })
     }
+
+    async fn count_tokens(
+        &self,
+        client: &reqwest::Client,
+        payload: CreateChatCompletionPayload,
+    ) -> Result<u64, ProviderError> {
+        let model = payload
+            .model
+            .clone()
+            .or_else(|| self.default_model.clone())
+            .unwrap_or_else(|| "claude-sonnet-4-20250514".to_string());
+
+        let mut messages_to_convert = payload.messages;
+        preprocess_messages_for_images(
+            client,
+            &mut messages_to_convert,
+            Some(&self.image_fetch_config),
+        )
+        .await;
+        let mid_conversation_system =
+            supports_mid_conversation_system(&model, &self.mid_conversation_system_models);
+        let (system, messages) = convert_messages(messages_to_convert, mid_conversation_system);
+        let tools = convert_tools(payload.tools);
+        let tool_choice = if tools.is_some() {
+            convert_tool_choice(payload.tool_choice)
+        } else {
+            None
+        };
+        let body = serde_json::to_vec(&AnthropicCountTokensRequest {
+            model,
+            messages,
+            system,
+            tools,
+            tool_choice,
+        })
+        .unwrap_or_default();
+
+        let url = format!("{}/v1/messages/count_tokens", self.base_url);
+        let api_key = self.api_key.clone();
+        let timeout = self.timeout;
+
+        let response = with_circuit_breaker_and_retry(
+            self.circuit_breaker.as_deref(),
+            &self.circuit_breaker_config,
+            &self.retry.for_read_only(),
+            "anthropic",
+            "count_tokens",
+            || async {
+                client
+                    .post(&url)
+                    .header("x-api-key", &api_key)
+                    .header("anthropic-version", ANTHROPIC_VERSION)
+                    .header("content-type", "application/json")
+                    .timeout(timeout)
+                    .body(body.clone())
+                    .send()
+                    .await
+            },
+        )
+        .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(ProviderError::Internal(format!(
+                "Anthropic token counting API error: {status} - {body}"
+            )));
+        }
+
+        let counted: AnthropicCountTokensResponse = response.json().await?;
+        Ok(counted.input_tokens)
+    }
 }
 
 #[cfg(test)]
@@ -327,7 +327,11 @@ impl<S> AnthropicToOpenAIStream<S> {
                 self.state.message_id = message.id;
                 self.state.model = message.model;
                 if let Some(usage) = message.usage {
-                    self.state.input_tokens = usage.input_tokens;
+                    // Anthropic's `input_tokens` excludes cache reads and
+                    // writes; report the full prompt like OpenAI does.
+                    self.state.input_tokens = usage.input_tokens
+                        + usage.cache_read_input_tokens
+                        + usage.cache_creation_input_tokens;
                     self.state.cache_read_input_tokens = usage.cache_read_input_tokens;
                     self.state.cache_creation_input_tokens = usage.cache_creation_input_tokens;
                 }
@@ -856,7 +860,11 @@ impl<S> AnthropicToResponsesStream<S> {
                     format!("msg_{}", strip_anthropic_prefix(&message.id, "msg_"));
                 self.state.model = message.model;
                 if let Some(usage) = message.usage {
-                    self.state.input_tokens = usage.input_tokens;
+                    // Anthropic's `input_tokens` excludes cache reads and
+                    // writes; report the full prompt like OpenAI does.
+                    self.state.input_tokens = usage.input_tokens
+                        + usage.cache_read_input_tokens
+                        + usage.cache_creation_input_tokens;
                     self.state.cache_read_input_tokens = usage.cache_read_input_tokens;
                     self.state.cache_creation_input_tokens = usage.cache_creation_input_tokens;
                 }
@@ -1262,13 +1270,19 @@ impl<S> AnthropicToResponsesStream<S> {
 
                 // Build response object with echo fields
                 let mut response_obj = self.build_response_json(status, serde_json::json!(output));
-                response_obj.insert("usage".into(), serde_json::json!({
-                    "input_tokens": self.state.input_tokens,
-                    "input_tokens_details": { "cached_tokens": self.state.cache_read_input_tokens },
-                    "output_tokens": self.state.output_tokens,
-                    "output_tokens_details": { "reasoning_tokens": 0 },
-                    "total_tokens": self.state.input_tokens + self.state.output_tokens
-                }));
+                response_obj.insert(
+                    "usage".into(),
+                    serde_json::json!({
+                        "input_tokens": self.state.input_tokens,
+                        "input_tokens_details": {
+                            "cached_tokens": self.state.cache_read_input_tokens,
+                            "cache_creation_input_tokens": self.state.cache_creation_input_tokens
+                        },
+                        "output_tokens": self.state.output_tokens,
+                        "output_tokens_details": { "reasoning_tokens": 0 },
+                        "total_tokens": self.state.input_tokens + self.state.output_tokens
+                    }),
+                );
                 if status == "completed" {
                     response_obj.insert(
                         "completed_at".into(),
@@ -33,7 +33,7 @@ pub struct AnthropicRequest {
     pub messages: Vec<AnthropicMessage>,
     pub max_tokens: u32,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub system: Option<String>,
+    pub system: Option<AnthropicSystem>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub temperature: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -59,6 +59,61 @@ pub struct AnthropicRequest {
     pub metadata: Option<AnthropicMetadata>,
 }
 
+impl AnthropicRequest {
+    /// Whether the caller placed any cache breakpoint in the request.
+    pub fn has_cache_breakpoint(&self) -> bool {
+        matches!(&self.system, Some(AnthropicSystem::Blocks(_)))
+            || self
+                .tools
+                .iter()
+                .flatten()
+                .any(|tool| tool.cache_control.is_some())
+            || self.messages.iter().any(|message| match &message.content {
+                AnthropicContent::Text(_) => false,
+                AnthropicContent::Blocks(blocks) => {
+                    blocks.iter().any(|block| block.cache_control().is_some())
+                }
+            })
+    }
+}
+
+/// Top-level system prompt.
+///
+/// Sent as a plain string unless it carries a cache breakpoint, which needs
+/// the content block form.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum AnthropicSystem {
+    Text(String),
+    Blocks(Vec<ContentBlock>),
+}
+
+impl AnthropicSystem {
+    pub fn new(text: String, cache_control: Option<AnthropicCacheControl>) -> Self {
+        match cache_control {
+            Some(cache_control) => Self::Blocks(vec![ContentBlock::Text {
+                text,
+                cache_control: Some(cache_control),
+            }]),
+            None => Self::Text(text),
+        }
+    }
+}
+
+/// Request body for `/v1/messages/count_tokens`, which rejects generation
+/// parameters such as `max_tokens`.
+#[derive(Debug, Serialize)]
+pub struct AnthropicCountTokensRequest {
+    pub model: String,
+    pub messages: Vec<AnthropicMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<AnthropicTool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<AnthropicToolChoice>,
+}
+
 /// Anthropic metadata for tracking
 #[derive(Debug, Serialize)]
 pub struct AnthropicMetadata {
@@ -199,6 +254,18 @@ pub enum ContentBlock {
     },
 }
 
+impl ContentBlock {
+    pub fn cache_control(&self) -> Option<&AnthropicCacheControl> {
+        match self {
+            Self::Text { cache_control, .. }
+            | Self::Image { cache_control, .. }
+            | Self::ToolUse { cache_control, .. }
+            | Self::ToolResult { cache_control, .. } => cache_control.as_ref(),
+            Self::Thinking { .. } => None,
+        }
+    }
+}
+
 /// Image source for Anthropic's Messages API.
 ///
 /// Anthropic supports two image source types:
@@ -224,6 +291,11 @@ pub struct AnthropicResponse {
     pub usage: AnthropicUsage,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct AnthropicCountTokensResponse {
+    pub input_tokens: u64,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct AnthropicUsage {
     pub input_tokens: i64,
@@ -236,6 +308,14 @@ pub struct AnthropicUsage {
     pub cache_creation_input_tokens: i64,
 }
 
+impl AnthropicUsage {
+    /// Total prompt tokens. Anthropic's `input_tokens` excludes cache reads
+    /// and writes, while OpenAI's `prompt_tokens` includes them.
+    pub fn prompt_tokens(&self) -> i64 {
+        self.input_tokens + self.cache_read_input_tokens + self.cache_creation_input_tokens
+    }
+}
+
 // ============================================================================
 // OpenAI Response Types (for format conversion)
 // ============================================================================
@@ -10,7 +10,7 @@
 
 mod token;
 
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{borrow::Cow, collections::HashMap, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
 use axum::response::Response;
@@ -24,8 +24,8 @@ use crate::{
     config::{AzureAuth, AzureOpenAiProviderConfig, CircuitBreakerConfig, RetryConfig},
     providers::{
         self, CircuitBreakerRegistry, ModelsResponse, Provider, ProviderError,
-        circuit_breaker::CircuitBreaker, error::AzureOpenAiErrorParser, response::error_response,
-        retry::with_circuit_breaker_and_retry,
+        circuit_breaker::CircuitBreaker, endpoints::select_endpoint, error::AzureOpenAiErrorParser,
+        response::error_response, retry::with_circuit_breaker_and_retry,
     },
 };
 
@@ -69,8 +69,23 @@ impl AzureOpenAIProvider {
         provider_name: &str,
         registry: &CircuitBreakerRegistry,
     ) -> Self {
-        let base = Self::build_base(config);
-        let circuit_breaker = registry.get_or_create(provider_name, &config.circuit_breaker);
+        let (config, circuit_breaker) = if config.endpoints.is_empty() {
+            let breaker = registry.get_or_create(provider_name, &config.circuit_breaker);
+            (Cow::Borrowed(config), breaker)
+        } else {
+            let (endpoint, breaker) = select_endpoint(
+                registry,
+                provider_name,
+                config.endpoint_selection,
+                &config.endpoints,
+                &config.circuit_breaker,
+            );
+            let config = endpoint.map_or(Cow::Borrowed(config), |e| {
+                Cow::Owned(config.with_endpoint(e))
+            });
+            (config, Some(breaker))
+        };
+        let base = Self::build_base(&config);
 
         Self {
             base_url: base.0,
@@ -27,6 +27,7 @@ use crate::{
             ResponsesUsage, ResponsesUsageInputTokensDetails, ResponsesUsageOutputTokensDetails,
         },
     },
+    config::PromptCachingConfig,
     providers::{
         convert_utils::{easy_content_text, input_content_text},
         image::parse_data_url,
@@ -410,12 +411,15 @@ pub(super) fn convert_response(bedrock: BedrockConverseResponse, model: &str) ->
             logprobs: None,
         }],
         usage: Some(OpenAIUsage {
-            prompt_tokens: bedrock.usage.input_tokens,
+            prompt_tokens: bedrock.usage.prompt_tokens(),
             completion_tokens: bedrock.usage.output_tokens,
-            total_tokens: bedrock.usage.input_tokens + bedrock.usage.output_tokens,
-            prompt_tokens_details: if bedrock.usage.cache_read_input_tokens > 0 {
+            total_tokens: bedrock.usage.prompt_tokens() + bedrock.usage.output_tokens,
+            prompt_tokens_details: if bedrock.usage.cache_read_input_tokens > 0
+                || bedrock.usage.cache_write_input_tokens > 0
+            {
                 Some(PromptTokensDetails {
                     cached_tokens: bedrock.usage.cache_read_input_tokens,
+                    cache_creation_input_tokens: bedrock.usage.cache_write_input_tokens,
                 })
             } else {
                 None
@@ -652,6 +656,24 @@ pub(super) fn convert_responses_input_to_bedrock_messages(
     (join_system_parts_bedrock(system_parts), messages)
 }
 
+/// Add a cache point after the system prompt when `prompt_caching` asks for
+/// it, the model supports cache points, and the caller hasn't placed any.
+pub(super) fn apply_prompt_caching(
+    request: &mut BedrockConverseRequest,
+    model: &str,
+    prompt_caching: &PromptCachingConfig,
+) {
+    if !(is_claude_model(model) || is_nova_model(model)) || request.has_cache_point() {
+        return;
+    }
+    if let Some(system) = &mut request.system {
+        let text: String = system.iter().filter_map(|s| s.text.as_deref()).collect();
+        if prompt_caching.caches_system_prompt(&text) {
+            system.push(BedrockSystemContent::cache_point());
+        }
+    }
+}
+
 /// Join collected system/developer prompt parts into Bedrock system blocks, or `None`.
 fn join_system_parts_bedrock(parts: Vec<String>) -> Option<Vec<BedrockSystemContent>> {
     if parts.is_empty() {
@@ -975,13 +997,14 @@ pub(super) fn convert_bedrock_to_responses_response(
         error: None,
         incomplete_details: None,
         usage: Some(ResponsesUsage {
-            input_tokens: bedrock.usage.input_tokens,
+            input_tokens: bedrock.usage.prompt_tokens(),
             input_tokens_details: ResponsesUsageInputTokensDetails {
                 cached_tokens: bedrock.usage.cache_read_input_tokens,
+                cache_creation_input_tokens: bedrock.usage.cache_write_input_tokens,
             },
             output_tokens: bedrock.usage.output_tokens,
             output_tokens_details: ResponsesUsageOutputTokensDetails { reasoning_tokens },
-            total_tokens: bedrock.usage.input_tokens + bedrock.usage.output_tokens,
+            total_tokens: bedrock.usage.prompt_tokens() + bedrock.usage.output_tokens,
             cost: None,
             is_byok: None,
             cost_details: None,
@@ -1580,14 +1603,16 @@ mod finish_reason_tests {
 
         let openai = convert_response(response, "test-model");
 
+        // Prompt tokens include cache reads and writes
         let usage = openai.usage.unwrap();
-        assert_eq!(usage.prompt_tokens, 100);
+        assert_eq!(usage.prompt_tokens, 135);
         assert_eq!(usage.completion_tokens, 50);
-        assert_eq!(usage.total_tokens, 150);
+        assert_eq!(usage.total_tokens, 185);
 
         // Should have cache tokens
         let details = usage.prompt_tokens_details.unwrap();
         assert_eq!(details.cached_tokens, 25);
+        assert_eq!(details.cache_creation_input_tokens, 10);
     }
 
     #[test]
@@ -2091,6 +2116,65 @@ mod cache_control_tests {
         assert_eq!(bedrock_msgs[0].role, "user");
     }
 
+    #[test]
+    fn test_apply_prompt_caching_marks_long_system_prompt() {
+        let prompt_caching = PromptCachingConfig {
+            auto_cache_system_prompt: true,
+            min_system_prompt_chars: 10,
+        };
+        let request = |system: &str, cache_control: Option<CacheControl>| {
+            let (system, messages) = convert_messages(vec![
+                Message::System {
+                    content: MessageContent::Text(system.to_string()),
+                    name: None,
+                },
+                Message::User {
+                    content: MessageContent::Parts(vec![ContentPart::Text {
+                        text: "Hello".to_string(),
+                        cache_control,
+                    }]),
+                    name: None,
+                },
+            ]);
+            BedrockConverseRequest {
+                messages,
+                system,
+                inference_config: None,
+                tool_config: None,
+                additional_model_request_fields: None,
+            }
+        };
+        let claude = "anthropic.claude-sonnet-4-20250514-v1:0";
+
+        let mut long = request("You are a helpful assistant.", None);
+        apply_prompt_caching(&mut long, claude, &prompt_caching);
+        let system = long.system.unwrap();
+        assert_eq!(system.len(), 2);
+        assert!(system[1].cache_point.is_some());
+
+        // Too short, unsupported model, disabled, or caller-placed cache points
+        let mut short = request("Be terse.", None);
+        apply_prompt_caching(&mut short, claude, &prompt_caching);
+        assert_eq!(short.system.unwrap().len(), 1);
+
+        let mut llama = request("You are a helpful assistant.", None);
+        apply_prompt_caching(&mut llama, "meta.llama3-70b-instruct-v1:0", &prompt_caching);
+        assert_eq!(llama.system.unwrap().len(), 1);
+
+        let mut disabled = request("You are a helpful assistant.", None);
+        apply_prompt_caching(&mut disabled, claude, &PromptCachingConfig::default());
+        assert_eq!(disabled.system.unwrap().len(), 1);
+
+        let mut explicit = request(
+            "You are a helpful assistant.",
+            Some(CacheControl {
+                type_: CacheControlType::Ephemeral,
+            }),
+        );
+        apply_prompt_caching(&mut explicit, claude, &prompt_caching);
+        assert_eq!(explicit.system.unwrap().len(), 1);
+    }
+
     #[test]
     fn test_convert_messages_developer_with_cache_control() {
         let messages = vec![
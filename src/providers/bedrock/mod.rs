@@ -35,7 +35,10 @@ use crate::{
             EmbeddingResponseObjectType, EmbeddingUsage, EmbeddingVector,
         },
     },
-    config::{BedrockProviderConfig, CircuitBreakerConfig, RetryConfig, StreamingBufferConfig},
+    config::{
+        BedrockProviderConfig, CircuitBreakerConfig, PromptCachingConfig, RetryConfig,
+        StreamingBufferConfig,
+    },
     providers::{
         CircuitBreakerRegistry, ModelInfo, ModelsResponse, Provider, ProviderError,
         aws::AwsRequestSigner,
@@ -112,6 +115,8 @@ pub struct BedrockProvider {
     /// `interleaved-thinking-2025-05-14` beta header (mirrors the Anthropic
     /// provider's allowlist).
     interleaved_thinking_models: Vec<String>,
+    /// Automatic prompt caching of system prompts
+    prompt_caching: PromptCachingConfig,
     /// Cached inference profiles
     inference_profile_cache: Arc<RwLock<InferenceProfileCache>>,
     /// Cached foundation models
@@ -163,6 +168,7 @@ impl BedrockProvider {
             image_fetch_config,
             converse_base_url_override: config.converse_base_url.clone(),
             interleaved_thinking_models: config.interleaved_thinking_models.clone(),
+            prompt_caching: config.prompt_caching.clone(),
             inference_profile_cache: Arc::new(RwLock::new(InferenceProfileCache::default())),
             foundation_models_cache: Arc::new(RwLock::new(FoundationModelsCache::default())),
         }
@@ -589,7 +595,7 @@ impl Provider for BedrockProvider {
             None
         };
 
-        let bedrock_request = BedrockConverseRequest {
+        let mut bedrock_request = BedrockConverseRequest {
             messages,
             system,
             inference_config: Some(BedrockInferenceConfig {
@@ -601,6 +607,7 @@ impl Provider for BedrockProvider {
             tool_config,
             additional_model_request_fields,
         };
+        apply_prompt_caching(&mut bedrock_request, &model, &self.prompt_caching);
 
         let body = serde_json::to_vec(&bedrock_request).unwrap_or_default();
 
@@ -730,7 +737,7 @@ impl Provider for BedrockProvider {
             None
         };
 
-        let bedrock_request = BedrockConverseRequest {
+        let mut bedrock_request = BedrockConverseRequest {
             messages,
             system,
             inference_config: Some(BedrockInferenceConfig {
@@ -742,6 +749,7 @@ impl Provider for BedrockProvider {
             tool_config,
             additional_model_request_fields,
         };
+        apply_prompt_caching(&mut bedrock_request, &model, &self.prompt_caching);
 
         let body = serde_json::to_vec(&bedrock_request).unwrap_or_default();
 
@@ -984,6 +992,8 @@ mod url_tests {
             models: HashMap::new(),
             retry: RetryConfig::default(),
             circuit_breaker: CircuitBreakerConfig::default(),
+            fair_queue: None,
+            adaptive_concurrency: None,
             streaming_buffer: StreamingBufferConfig::default(),
             fallback_providers: Vec::new(),
             model_fallbacks: HashMap::new(),
@@ -991,7 +1001,9 @@ mod url_tests {
             health_check: Default::default(),
             catalog_provider: None,
             sovereignty: None,
+            max_response_bytes: None,
             interleaved_thinking_models: crate::config::default_interleaved_thinking_models(),
+            prompt_caching: Default::default(),
         };
         let registry = CircuitBreakerRegistry::default();
         BedrockProvider::from_config_with_registry(&config, "test", &registry)
@@ -47,6 +47,8 @@ pub(super) struct StreamState {
     pub output_tokens: i64,
     /// Cached tokens read from prompt cache
     pub cache_read_input_tokens: i64,
+    /// Tokens written to the prompt cache
+    pub cache_write_input_tokens: i64,
     /// Buffer for incomplete event stream data
     pub buffer: bytes::BytesMut,
     /// Event stream frame decoder
@@ -355,9 +357,14 @@ impl<S> BedrockToOpenAIStream<S> {
             }
             "metadata" => {
                 if let Ok(metadata) = serde_json::from_slice::<BedrockMetadata>(payload) {
-                    self.state.input_tokens = metadata.usage.input_tokens;
+                    // Bedrock's `inputTokens` excludes cache reads and
+                    // writes; report the full prompt like OpenAI does.
+                    self.state.input_tokens = metadata.usage.input_tokens
+                        + metadata.usage.cache_read_input_tokens
+                        + metadata.usage.cache_write_input_tokens;
                     self.state.output_tokens = metadata.usage.output_tokens;
                     self.state.cache_read_input_tokens = metadata.usage.cache_read_input_tokens;
+                    self.state.cache_write_input_tokens = metadata.usage.cache_write_input_tokens;
 
                     // Emit final chunk with usage
                     let usage_chunk = OpenAIStreamChunk {
@@ -370,9 +377,14 @@ impl<S> BedrockToOpenAIStream<S> {
                             prompt_tokens: self.state.input_tokens,
                             completion_tokens: self.state.output_tokens,
                             total_tokens: self.state.input_tokens + self.state.output_tokens,
-                            prompt_tokens_details: if self.state.cache_read_input_tokens > 0 {
+                            prompt_tokens_details: if self.state.cache_read_input_tokens > 0
+                                || self.state.cache_write_input_tokens > 0
+                            {
                                 Some(StreamPromptTokensDetails {
                                     cached_tokens: self.state.cache_read_input_tokens,
+                                    cache_creation_input_tokens: self
+                                        .state
+                                        .cache_write_input_tokens,
                                 })
                             } else {
                                 None
@@ -511,6 +523,8 @@ pub(super) struct ResponsesStreamState {
     pub output_tokens: i64,
     /// Cached tokens read from prompt cache
     pub cache_read_input_tokens: i64,
+    /// Tokens written to the prompt cache
+    pub cache_write_input_tokens: i64,
     /// Accumulated text content
     pub text_content: String,
     /// Tracks tool calls: (block_index, tool_id, tool_name, arguments)
@@ -906,9 +920,14 @@ impl<S> BedrockToResponsesStream<S> {
             }
             "metadata" => {
                 if let Ok(metadata) = serde_json::from_slice::<BedrockMetadata>(payload) {
-                    self.state.input_tokens = metadata.usage.input_tokens;
+                    // Bedrock's `inputTokens` excludes cache reads and
+                    // writes; report the full prompt like OpenAI does.
+                    self.state.input_tokens = metadata.usage.input_tokens
+                        + metadata.usage.cache_read_input_tokens
+                        + metadata.usage.cache_write_input_tokens;
                     self.state.output_tokens = metadata.usage.output_tokens;
                     self.state.cache_read_input_tokens = metadata.usage.cache_read_input_tokens;
+                    self.state.cache_write_input_tokens = metadata.usage.cache_write_input_tokens;
 
                     // Emit completion events
 
@@ -1099,13 +1118,19 @@ impl<S> BedrockToResponsesStream<S> {
                     // Build response object with echo fields
                     let mut response_obj =
                         self.build_response_json(status, serde_json::json!(output));
-                    response_obj.insert("usage".into(), serde_json::json!({
-                        "input_tokens": self.state.input_tokens,
-                        "input_tokens_details": { "cached_tokens": self.state.cache_read_input_tokens },
-                        "output_tokens": self.state.output_tokens,
-                        "output_tokens_details": { "reasoning_tokens": 0 },
-                        "total_tokens": self.state.input_tokens + self.state.output_tokens
-                    }));
+                    response_obj.insert(
+                        "usage".into(),
+                        serde_json::json!({
+                            "input_tokens": self.state.input_tokens,
+                            "input_tokens_details": {
+                                "cached_tokens": self.state.cache_read_input_tokens,
+                                "cache_creation_input_tokens": self.state.cache_write_input_tokens
+                            },
+                            "output_tokens": self.state.output_tokens,
+                            "output_tokens_details": { "reasoning_tokens": 0 },
+                            "total_tokens": self.state.input_tokens + self.state.output_tokens
+                        }),
+                    );
                     if status == "completed" {
                         response_obj.insert(
                             "completed_at".into(),
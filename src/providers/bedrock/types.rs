@@ -27,6 +27,26 @@ pub(super) struct BedrockConverseRequest {
     pub additional_model_request_fields: Option<serde_json::Value>,
 }
 
+impl BedrockConverseRequest {
+    /// Whether the caller placed any cache point in the request.
+    pub fn has_cache_point(&self) -> bool {
+        self.system
+            .iter()
+            .flatten()
+            .any(|s| s.cache_point.is_some())
+            || self
+                .tool_config
+                .iter()
+                .flat_map(|c| &c.tools)
+                .any(|t| t.cache_point.is_some())
+            || self
+                .messages
+                .iter()
+                .flat_map(|m| &m.content)
+                .any(|c| c.cache_point.is_some())
+    }
+}
+
 /// System content block for Bedrock messages
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -364,18 +384,25 @@ pub(super) struct BedrockReasoningText {
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(super) struct BedrockUsage {
+    /// Uncached prompt tokens; excludes cache reads and writes.
     pub input_tokens: i64,
     pub output_tokens: i64,
     /// Tokens read from the prompt cache (cache hit)
     #[serde(default)]
     pub cache_read_input_tokens: i64,
     /// Tokens written to the prompt cache (cache miss, will be cached).
-    /// Captured for observability; OpenAI format doesn't have a field for this.
     #[serde(default)]
-    #[allow(dead_code)] // Deserialization field
     pub cache_write_input_tokens: i64,
 }
 
+impl BedrockUsage {
+    /// Total prompt tokens, including cache reads and writes, as OpenAI
+    /// reports `prompt_tokens`.
+    pub fn prompt_tokens(&self) -> i64 {
+        self.input_tokens + self.cache_read_input_tokens + self.cache_write_input_tokens
+    }
+}
+
 // ============================================================================
 // OpenAI Response Types (for format conversion)
 // ============================================================================
@@ -432,6 +459,13 @@ pub(super) struct OpenAIToolCallFunction {
 pub(super) struct PromptTokensDetails {
     /// Cached tokens read from prompt cache
     pub cached_tokens: i64,
+    /// Tokens written to the prompt cache (cache miss, will be cached)
+    #[serde(skip_serializing_if = "is_zero")]
+    pub cache_creation_input_tokens: i64,
+}
+
+fn is_zero(v: &i64) -> bool {
+    *v == 0
 }
 
 #[derive(Debug, Serialize)]
@@ -527,6 +561,9 @@ pub(super) struct OpenAIStreamFunction {
 pub(super) struct StreamPromptTokensDetails {
     /// Cached tokens read from prompt cache
     pub cached_tokens: i64,
+    /// Tokens written to the prompt cache (cache miss, will be cached)
+    #[serde(skip_serializing_if = "is_zero")]
+    pub cache_creation_input_tokens: i64,
 }
 
 #[derive(Debug, Serialize)]
@@ -638,9 +675,7 @@ pub(super) struct BedrockStreamUsage {
     #[serde(default)]
     pub cache_read_input_tokens: i64,
     /// Tokens written to the prompt cache (cache miss, will be cached).
-    /// Captured for observability; OpenAI format doesn't have a field for this.
     #[serde(default)]
-    #[allow(dead_code)] // Deserialization field
     pub cache_write_input_tokens: i64,
 }
 
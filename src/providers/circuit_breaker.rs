@@ -24,6 +24,13 @@
 //!     breaker.record_failure();
 //! }
 //! ```
+//!
+//! # Shared Mode
+//!
+//! With [`CircuitBreakerMode::Shared`], opens are exchanged with other nodes
+//! through the cache by the circuit breaker sync job: [`CircuitBreaker::open_state`]
+//! is published when this node trips, and [`CircuitBreaker::apply_shared_open`]
+//! opens the local circuit when another node has tripped.
 
 use std::{
     sync::{
@@ -34,19 +41,22 @@ use std::{
 };
 
 use chrono::Utc;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tracing::{debug, info, warn};
 
 use crate::{
-    config::CircuitBreakerConfig,
+    config::{CircuitBreakerConfig, CircuitBreakerMode},
     events::{CircuitBreakerState as EventCBState, EventBus, ServerEvent},
     observability::metrics,
 };
 
 const MAX_CAS_RETRIES: usize = 100;
+/// Weight of the newest sample in the response latency moving average.
+const LATENCY_EWMA_ALPHA: f64 = 0.3;
 
 /// Circuit breaker state.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum CircuitState {
@@ -70,6 +80,24 @@ pub enum CircuitBreakerError {
     },
 }
 
+/// An open circuit, as shared between nodes in [`CircuitBreakerMode::Shared`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SharedOpenState {
+    /// When the circuit opened (millis since UNIX epoch).
+    pub opened_at_millis: u64,
+    /// How long the circuit stays open, including adaptive backoff.
+    pub timeout_millis: u64,
+    /// Consecutive opens, so backoff carries over to the node that probes next.
+    pub consecutive_opens: u32,
+}
+
+impl SharedOpenState {
+    /// When the circuit moves to half-open (millis since UNIX epoch).
+    pub fn open_until_millis(&self) -> u64 {
+        self.opened_at_millis + self.timeout_millis
+    }
+}
+
 // State encoding: upper 2 bits = state, lower 30 bits = counter
 const STATE_CLOSED: u32 = 0;
 const STATE_OPEN: u32 = 1;
@@ -94,6 +122,9 @@ pub struct CircuitBreaker {
     /// Number of consecutive times the circuit has opened without successful recovery.
     /// Used for exponential backoff calculation.
     consecutive_opens: AtomicU32,
+    /// Moving average of successful response latency in microseconds
+    /// (0 until the first sample). Used to pick between provider endpoints.
+    latency_ewma_micros: AtomicU64,
     /// Optional event bus for broadcasting state changes.
     event_bus: Option<Arc<EventBus>>,
 }
@@ -109,6 +140,7 @@ impl CircuitBreaker {
             opened_at: AtomicU64::new(0),
             current_timeout_millis: AtomicU64::new(initial_timeout_millis),
             consecutive_opens: AtomicU32::new(0),
+            latency_ewma_micros: AtomicU64::new(0),
             event_bus: None,
         }
     }
@@ -127,6 +159,7 @@ impl CircuitBreaker {
             opened_at: AtomicU64::new(0),
             current_timeout_millis: AtomicU64::new(initial_timeout_millis),
             consecutive_opens: AtomicU32::new(0),
+            latency_ewma_micros: AtomicU64::new(0),
             event_bus: Some(event_bus),
         }
     }
@@ -349,6 +382,91 @@ impl CircuitBreaker {
         if state == STATE_CLOSED { counter } else { 0 }
     }
 
+    /// Record the latency of a successful response. Tracked even when the
+    /// breaker is disabled.
+    pub fn record_latency(&self, latency: Duration) {
+        let sample = (latency.as_micros() as u64).max(1);
+        let _ =
+            self.latency_ewma_micros
+                .fetch_update(Ordering::AcqRel, Ordering::Acquire, |current| {
+                    Some(if current == 0 {
+                        sample
+                    } else {
+                        (LATENCY_EWMA_ALPHA * sample as f64
+                            + (1.0 - LATENCY_EWMA_ALPHA) * current as f64)
+                            .round() as u64
+                    })
+                });
+    }
+
+    /// Moving average of recent response latency, if any was recorded.
+    pub fn latency(&self) -> Option<Duration> {
+        match self.latency_ewma_micros.load(Ordering::Acquire) {
+            0 => None,
+            micros => Some(Duration::from_micros(micros)),
+        }
+    }
+
+    /// Whether opens are shared with other nodes.
+    pub fn is_shared(&self) -> bool {
+        self.config.enabled && self.config.mode == CircuitBreakerMode::Shared
+    }
+
+    /// The open circuit, if the breaker is open.
+    pub fn open_state(&self) -> Option<SharedOpenState> {
+        let packed = self.state_and_counter.load(Ordering::Acquire);
+        let (state, _) = unpack_state(packed);
+        (state == STATE_OPEN).then(|| SharedOpenState {
+            opened_at_millis: self.opened_at.load(Ordering::Acquire),
+            timeout_millis: self.current_timeout_millis.load(Ordering::Acquire),
+            consecutive_opens: self.consecutive_opens.load(Ordering::Acquire),
+        })
+    }
+
+    /// Open the circuit because another node opened it.
+    ///
+    /// Does nothing if the shared open has already expired or this breaker is
+    /// open until at least as late. Returns whether the circuit was opened.
+    pub fn apply_shared_open(&self, shared: SharedOpenState) -> bool {
+        if !self.config.enabled || shared.open_until_millis() <= current_time_millis() {
+            return false;
+        }
+        if let Some(local) = self.open_state()
+            && local.open_until_millis() >= shared.open_until_millis()
+        {
+            return false;
+        }
+
+        let previous_state = self.state();
+        self.consecutive_opens
+            .store(shared.consecutive_opens, Ordering::Release);
+        self.current_timeout_millis
+            .store(shared.timeout_millis, Ordering::Release);
+        self.opened_at
+            .store(shared.opened_at_millis, Ordering::Release);
+        self.state_and_counter
+            .store(pack_state(STATE_OPEN, 0), Ordering::Release);
+
+        warn!(
+            provider = %self.provider_name,
+            timeout_secs = shared.timeout_millis / 1000,
+            consecutive_opens = shared.consecutive_opens,
+            "Circuit breaker OPENED - opened by another node"
+        );
+        metrics::record_circuit_breaker_state(&self.provider_name, "open");
+        metrics::record_circuit_breaker_consecutive_opens(
+            &self.provider_name,
+            shared.consecutive_opens,
+        );
+        metrics::record_circuit_breaker_failures(
+            &self.provider_name,
+            0,
+            self.config.failure_threshold,
+        );
+        self.publish_state_change(previous_state, CircuitState::Open);
+        true
+    }
+
     fn transition_to_open(&self) {
         let previous_state = self.state();
 
@@ -488,6 +606,7 @@ mod tests {
             failure_status_codes: vec![500, 502, 503, 504],
             backoff_multiplier: 2.0,
             max_open_timeout_secs: 300,
+            mode: CircuitBreakerMode::Local,
         }
     }
 
@@ -721,6 +840,7 @@ mod tests {
             failure_status_codes: vec![500],
             backoff_multiplier: 2.0,
             max_open_timeout_secs: 100,
+            mode: CircuitBreakerMode::Local,
         };
 
         // First open: 10s
@@ -747,6 +867,7 @@ mod tests {
             failure_status_codes: vec![500],
             backoff_multiplier: 1.0, // Disables adaptive backoff
             max_open_timeout_secs: 300,
+            mode: CircuitBreakerMode::Local,
         };
 
         // All opens should use base timeout
@@ -766,6 +887,7 @@ mod tests {
             failure_status_codes: vec![500],
             backoff_multiplier: 2.0,
             max_open_timeout_secs: 300,
+            mode: CircuitBreakerMode::Local,
         };
         let breaker = CircuitBreaker::new("test", &config);
 
@@ -811,6 +933,7 @@ mod tests {
             failure_status_codes: vec![500],
             backoff_multiplier: 2.0,
             max_open_timeout_secs: 300,
+            mode: CircuitBreakerMode::Local,
         };
         let breaker = CircuitBreaker::new("test", &config);
 
@@ -855,6 +978,7 @@ mod tests {
             failure_status_codes: vec![500],
             backoff_multiplier: 3.0, // Aggressive multiplier
             max_open_timeout_secs: 120,
+            mode: CircuitBreakerMode::Local,
         };
         let breaker = CircuitBreaker::new("test", &config);
 
@@ -883,4 +1007,46 @@ mod tests {
         breaker.record_failure();
         assert_eq!(breaker.current_timeout_secs(), 120);
     }
+
+    #[test]
+    fn test_apply_shared_open() {
+        let tripped = CircuitBreaker::new("test", &test_config());
+        let follower = CircuitBreaker::new("test", &test_config());
+
+        assert!(tripped.open_state().is_none());
+        for _ in 0..3 {
+            tripped.record_failure();
+        }
+        let shared = tripped.open_state().unwrap();
+        assert_eq!(shared.consecutive_opens, 1);
+
+        assert!(follower.apply_shared_open(shared));
+        assert_eq!(follower.state(), CircuitState::Open);
+        assert!(follower.check().is_err());
+        assert_eq!(follower.open_state(), Some(shared));
+
+        // Already open until then: applying again is a no-op
+        assert!(!follower.apply_shared_open(shared));
+
+        // Opens that have already expired are ignored
+        let expired = CircuitBreaker::new("test", &test_config());
+        assert!(!expired.apply_shared_open(SharedOpenState {
+            opened_at_millis: shared.opened_at_millis - 10_000,
+            ..shared
+        }));
+        assert_eq!(expired.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_latency_moving_average() {
+        let breaker = CircuitBreaker::new("test", &CircuitBreakerConfig::default());
+        assert_eq!(breaker.latency(), None);
+
+        breaker.record_latency(Duration::from_millis(100));
+        assert_eq!(breaker.latency(), Some(Duration::from_millis(100)));
+
+        // Newer samples pull the average toward them
+        breaker.record_latency(Duration::from_millis(200));
+        assert_eq!(breaker.latency(), Some(Duration::from_millis(130)));
+    }
 }
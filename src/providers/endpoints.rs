@@ -0,0 +1,202 @@
+//! Endpoint selection for providers with several upstream endpoints.
+//!
+//! A provider's own connection settings form the `primary` endpoint, and
+//! `endpoints` in its config add more (e.g. Azure resources in other
+//! regions). Each endpoint has a circuit breaker in the
+//! [`CircuitBreakerRegistry`] under `<provider>/<endpoint>`, which also tracks
+//! its response latency. Providers are built per request, and pick an
+//! endpoint when they are built.
+
+use std::sync::Arc;
+
+use super::{CircuitBreakerRegistry, circuit_breaker::CircuitBreaker};
+use crate::config::{CircuitBreakerConfig, EndpointSelection, PRIMARY_ENDPOINT, ProviderEndpoint};
+
+/// With `lowest_latency`, one request in this many goes to the next endpoint
+/// in turn, so the latency of slower endpoints stays current.
+const LATENCY_EXPLORE_EVERY: usize = 20;
+
+/// Pick the endpoint for a request.
+///
+/// Returns the chosen endpoint (`None` for the primary) and its circuit
+/// breaker. Endpoints whose breaker is open are skipped unless all are.
+pub fn select_endpoint<'a>(
+    registry: &CircuitBreakerRegistry,
+    provider_name: &str,
+    selection: EndpointSelection,
+    endpoints: &'a [ProviderEndpoint],
+    config: &CircuitBreakerConfig,
+) -> (Option<&'a ProviderEndpoint>, Arc<CircuitBreaker>) {
+    let candidates: Vec<(Option<&ProviderEndpoint>, Arc<CircuitBreaker>)> = std::iter::once(None)
+        .chain(endpoints.iter().map(Some))
+        .map(|endpoint| {
+            let name = endpoint.map_or(PRIMARY_ENDPOINT, |e| e.name.as_str());
+            let breaker = registry.get_or_create_endpoint(provider_name, name, config);
+            (endpoint, breaker)
+        })
+        .collect();
+
+    let mut available: Vec<usize> = (0..candidates.len())
+        .filter(|&i| candidates[i].1.check().is_ok())
+        .collect();
+    if available.is_empty() {
+        available = (0..candidates.len()).collect();
+    }
+
+    let priority = |i: usize| candidates[i].0.map_or(0, |e| e.priority);
+    let chosen = match selection {
+        EndpointSelection::Priority => available
+            .iter()
+            .copied()
+            .min_by_key(|&i| (priority(i), i))
+            .unwrap_or(0),
+        EndpointSelection::RoundRobin => {
+            available[registry.next_endpoint_turn(provider_name) % available.len()]
+        }
+        EndpointSelection::LowestLatency => {
+            let turn = registry.next_endpoint_turn(provider_name);
+            let unsampled = available
+                .iter()
+                .copied()
+                .find(|&i| candidates[i].1.latency().is_none());
+            match unsampled {
+                Some(i) => i,
+                None if turn % LATENCY_EXPLORE_EVERY == LATENCY_EXPLORE_EVERY - 1 => {
+                    available[(turn / LATENCY_EXPLORE_EVERY) % available.len()]
+                }
+                None => available
+                    .iter()
+                    .copied()
+                    .min_by_key(|&i| candidates[i].1.latency())
+                    .unwrap_or(0),
+            }
+        }
+    };
+
+    let (endpoint, breaker) = candidates.into_iter().nth(chosen).expect("valid index");
+    (endpoint, breaker)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn endpoint(name: &str, priority: u32) -> ProviderEndpoint {
+        ProviderEndpoint {
+            name: name.to_string(),
+            base_url: Some(format!("https://{name}.example.com/v1")),
+            resource_name: None,
+            region: None,
+            api_key: None,
+            priority,
+        }
+    }
+
+    fn breaker_config() -> CircuitBreakerConfig {
+        CircuitBreakerConfig {
+            enabled: true,
+            failure_threshold: 1,
+            ..Default::default()
+        }
+    }
+
+    fn select<'a>(
+        registry: &CircuitBreakerRegistry,
+        selection: EndpointSelection,
+        endpoints: &'a [ProviderEndpoint],
+    ) -> &'a str {
+        select_endpoint(registry, "azure", selection, endpoints, &breaker_config())
+            .0
+            .map_or(PRIMARY_ENDPOINT, |e| e.name.as_str())
+    }
+
+    #[test]
+    fn test_priority_prefers_primary_then_lowest_priority() {
+        let registry = CircuitBreakerRegistry::new();
+        let endpoints = vec![endpoint("westus", 2), endpoint("eastus", 1)];
+
+        assert_eq!(
+            select(&registry, EndpointSelection::Priority, &endpoints),
+            "primary"
+        );
+
+        // Failing over skips the open primary
+        registry
+            .get_or_create_endpoint("azure", PRIMARY_ENDPOINT, &breaker_config())
+            .record_failure();
+        assert_eq!(
+            select(&registry, EndpointSelection::Priority, &endpoints),
+            "eastus"
+        );
+    }
+
+    #[test]
+    fn test_round_robin_rotates_over_available_endpoints() {
+        let registry = CircuitBreakerRegistry::new();
+        let endpoints = vec![endpoint("eastus", 0), endpoint("westus", 0)];
+
+        let picks: Vec<_> = (0..4)
+            .map(|_| select(&registry, EndpointSelection::RoundRobin, &endpoints))
+            .collect();
+        assert_eq!(picks, ["primary", "eastus", "westus", "primary"]);
+
+        registry
+            .get_or_create_endpoint("azure", "eastus", &breaker_config())
+            .record_failure();
+        for _ in 0..4 {
+            assert_ne!(
+                select(&registry, EndpointSelection::RoundRobin, &endpoints),
+                "eastus"
+            );
+        }
+    }
+
+    #[test]
+    fn test_lowest_latency_samples_then_prefers_fastest() {
+        let registry = CircuitBreakerRegistry::new();
+        let endpoints = vec![endpoint("eastus", 0)];
+
+        // Endpoints without samples are tried first
+        assert_eq!(
+            select(&registry, EndpointSelection::LowestLatency, &endpoints),
+            "primary"
+        );
+        registry
+            .get_or_create_endpoint("azure", PRIMARY_ENDPOINT, &breaker_config())
+            .record_latency(Duration::from_millis(400));
+        assert_eq!(
+            select(&registry, EndpointSelection::LowestLatency, &endpoints),
+            "eastus"
+        );
+        registry
+            .get_or_create_endpoint("azure", "eastus", &breaker_config())
+            .record_latency(Duration::from_millis(90));
+
+        let picks: Vec<_> = (0..LATENCY_EXPLORE_EVERY)
+            .map(|_| select(&registry, EndpointSelection::LowestLatency, &endpoints))
+            .collect();
+        assert_eq!(picks.iter().filter(|p| **p == "eastus").count(), 19);
+    }
+
+    #[test]
+    fn test_all_open_still_selects_an_endpoint() {
+        let registry = CircuitBreakerRegistry::new();
+        let endpoints = vec![endpoint("eastus", 1)];
+        for name in [PRIMARY_ENDPOINT, "eastus"] {
+            registry
+                .get_or_create_endpoint("azure", name, &breaker_config())
+                .record_failure();
+        }
+
+        let (_, breaker) = select_endpoint(
+            &registry,
+            "azure",
+            EndpointSelection::Priority,
+            &endpoints,
+            &breaker_config(),
+        );
+        assert!(breaker.check().is_err());
+    }
+}
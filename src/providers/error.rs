@@ -45,6 +45,188 @@ impl std::fmt::Display for OpenAiErrorType {
     }
 }
 
+/// Response header carrying the [`ErrorCategory`] of an error response, so
+/// usage tracking can record it without re-parsing the body.
+pub const ERROR_CATEGORY_HEADER: &str = "X-Error-Category";
+
+/// Gateway-wide error taxonomy.
+///
+/// Every error response carries one of these in `error.category`, whether the
+/// gateway produced it (budget, rate limit) or a provider did. Provider codes
+/// are mapped from each provider's native format, so clients and usage
+/// analytics can treat "Anthropic overloaded" and "Bedrock throttled" the same
+/// way as their OpenAI equivalents. `error.code` keeps the specific code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCategory {
+    /// Too many requests, from the gateway's limits or the provider's.
+    RateLimited,
+    /// The provider account is out of credit or quota.
+    QuotaExceeded,
+    /// A budget configured in the gateway was exceeded.
+    BudgetExceeded,
+    /// Blocked by a provider safety filter or a gateway guardrail.
+    ContentFiltered,
+    /// Input (plus requested output) exceeds the model's context window.
+    ContextLength,
+    /// Malformed request or unsupported parameter.
+    InvalidRequest,
+    /// Missing or invalid credentials.
+    Authentication,
+    /// Valid credentials without access to the resource or model.
+    PermissionDenied,
+    /// Unknown model or resource.
+    NotFound,
+    /// The provider is overloaded, unreachable, or shed by the gateway
+    /// (circuit breaker, fair queue).
+    ProviderUnavailable,
+    /// The provider did not respond in time.
+    Timeout,
+    /// Any other provider-side failure.
+    ProviderError,
+    /// A failure inside the gateway.
+    Internal,
+}
+
+impl ErrorCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::RateLimited => "rate_limited",
+            Self::QuotaExceeded => "quota_exceeded",
+            Self::BudgetExceeded => "budget_exceeded",
+            Self::ContentFiltered => "content_filtered",
+            Self::ContextLength => "context_length",
+            Self::InvalidRequest => "invalid_request",
+            Self::Authentication => "authentication",
+            Self::PermissionDenied => "permission_denied",
+            Self::NotFound => "not_found",
+            Self::ProviderUnavailable => "provider_unavailable",
+            Self::Timeout => "timeout",
+            Self::ProviderError => "provider_error",
+            Self::Internal => "internal",
+        }
+    }
+
+    /// Classify an error returned by a provider from its HTTP status and
+    /// native error code and message.
+    ///
+    /// Known native codes win, then message patterns for context length and
+    /// content filtering (which most providers report as a generic invalid
+    /// request), then the status code. Unrecognized 5xx responses are
+    /// [`ErrorCategory::ProviderError`].
+    pub fn classify_upstream(status: StatusCode, code: &str, message: &str) -> Self {
+        match Self::classify(status, code, message) {
+            Self::Internal => Self::ProviderError,
+            category => category,
+        }
+    }
+
+    /// Classify an error of unknown origin, such as one produced by the
+    /// gateway itself. Unrecognized 5xx responses are
+    /// [`ErrorCategory::Internal`].
+    pub fn classify(status: StatusCode, code: &str, message: &str) -> Self {
+        Self::from_native_code(code)
+            .or_else(|| Self::from_message(message))
+            .unwrap_or_else(|| Self::from_status(status))
+    }
+
+    /// Map gateway and provider error codes onto the taxonomy.
+    fn from_native_code(code: &str) -> Option<Self> {
+        let code = code.to_ascii_lowercase();
+        let category = match code.as_str() {
+            // Gateway codes
+            "budget_exceeded" => Self::BudgetExceeded,
+            "rate_limit_exceeded" => Self::RateLimited,
+            "circuit_breaker_open" | "provider_at_capacity" | "request_failed" => {
+                Self::ProviderUnavailable
+            }
+            "guardrails_blocked" | "guardrails_output_blocked" => Self::ContentFiltered,
+            // OpenAI / Azure OpenAI
+            "context_length_exceeded" | "string_above_max_length" => Self::ContextLength,
+            "content_filter" | "content_policy_violation" | "responsibleaipolicyviolation" => {
+                Self::ContentFiltered
+            }
+            "insufficient_quota" | "billing_hard_limit_reached" => Self::QuotaExceeded,
+            "invalid_api_key" | "invalid_authentication" => Self::Authentication,
+            "model_not_found" | "deploymentnotfound" => Self::NotFound,
+            "ratelimitexceeded" | "too_many_requests" => Self::RateLimited,
+            // Anthropic
+            "rate_limit_error" => Self::RateLimited,
+            "overloaded_error" => Self::ProviderUnavailable,
+            "authentication_error" => Self::Authentication,
+            "permission_error" => Self::PermissionDenied,
+            "not_found_error" => Self::NotFound,
+            "request_too_large" => Self::ContextLength,
+            // Bedrock
+            "throttlingexception" => Self::RateLimited,
+            "servicequotaexceededexception" => Self::QuotaExceeded,
+            "accessdeniedexception" => Self::PermissionDenied,
+            "unrecognizedclientexception" | "expiredtokenexception" => Self::Authentication,
+            "resourcenotfoundexception" | "modelnotfoundexception" => Self::NotFound,
+            "modeltimeoutexception" => Self::Timeout,
+            "modelnotreadyexception" | "serviceunavailableexception" => Self::ProviderUnavailable,
+            // Vertex AI (google.rpc.Code names)
+            "resource_exhausted" => Self::RateLimited,
+            "unauthenticated" => Self::Authentication,
+            "permission_denied" => Self::PermissionDenied,
+            "not_found" => Self::NotFound,
+            "deadline_exceeded" => Self::Timeout,
+            "unavailable" => Self::ProviderUnavailable,
+            _ => return None,
+        };
+        Some(category)
+    }
+
+    /// Providers without a dedicated code for these report them as a generic
+    /// invalid request, so fall back to the message text.
+    fn from_message(message: &str) -> Option<Self> {
+        let message = message.to_ascii_lowercase();
+        const CONTEXT_LENGTH: &[&str] = &[
+            "context length",
+            "context window",
+            "maximum context",
+            "prompt is too long",
+            "input is too long",
+            "too many input tokens",
+            "exceeds the maximum number of tokens",
+        ];
+        const CONTENT_FILTERED: &[&str] = &[
+            "content filter",
+            "content management policy",
+            "safety filter",
+            "blocked by guardrail",
+        ];
+        if CONTEXT_LENGTH.iter().any(|p| message.contains(p)) {
+            Some(Self::ContextLength)
+        } else if CONTENT_FILTERED.iter().any(|p| message.contains(p)) {
+            Some(Self::ContentFiltered)
+        } else {
+            None
+        }
+    }
+
+    fn from_status(status: StatusCode) -> Self {
+        match status.as_u16() {
+            401 => Self::Authentication,
+            402 => Self::BudgetExceeded,
+            403 => Self::PermissionDenied,
+            404 => Self::NotFound,
+            408 | 504 => Self::Timeout,
+            413 => Self::ContextLength,
+            429 => Self::RateLimited,
+            // 529 is Anthropic's "overloaded"
+            502 | 503 | 529 => Self::ProviderUnavailable,
+            400..=499 => Self::InvalidRequest,
+            _ => Self::Internal,
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Provider error information extracted from a provider's error response.
 #[derive(Debug, Clone)]
 pub struct ProviderErrorInfo {
@@ -114,6 +296,9 @@ pub struct OpenAiErrorBody {
     #[serde(rename = "type")]
     pub error_type: String,
     pub code: String,
+    /// **Hadrian Extension:** [`ErrorCategory`] of the error.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
 }
 
 /// Build an OpenAI-compatible error response from provider error info.
@@ -124,22 +309,63 @@ pub fn build_provider_error_response(
     status: StatusCode,
     error_info: ProviderErrorInfo,
 ) -> Result<Response, super::ProviderError> {
+    let category = ErrorCategory::classify_upstream(status, &error_info.code, &error_info.message);
     let response_body = OpenAiErrorResponse {
         error: OpenAiErrorBody {
             message: error_info.message,
             error_type: error_info.error_type.as_str().to_string(),
             code: error_info.code.to_lowercase(),
+            category: Some(category.as_str().to_string()),
         },
     };
 
     Ok(Response::builder()
         .status(status)
         .header("content-type", "application/json")
+        .header(ERROR_CATEGORY_HEADER, category.as_str())
         .body(Body::from(
             serde_json::to_string(&response_body).unwrap_or_default(),
         ))?)
 }
 
+/// Tag an OpenAI-format error body passed through from an OpenAI-compatible
+/// provider with its [`ErrorCategory`].
+///
+/// The body is otherwise left as the provider sent it. Bodies that aren't
+/// JSON objects are passed through untouched and classified by status.
+pub fn categorize_passthrough_error(status: StatusCode, body: &[u8]) -> (ErrorCategory, Vec<u8>) {
+    let Ok(mut json) = serde_json::from_slice::<serde_json::Value>(body) else {
+        return (
+            ErrorCategory::classify_upstream(status, "", ""),
+            body.to_vec(),
+        );
+    };
+    let Some(error) = json.get_mut("error").and_then(|e| e.as_object_mut()) else {
+        return (
+            ErrorCategory::classify_upstream(status, "", ""),
+            body.to_vec(),
+        );
+    };
+    // OpenAI puts the specific code in `code` and falls back to `type`
+    // (e.g. `insufficient_quota`) when `code` is null.
+    let code = error
+        .get("code")
+        .and_then(|c| c.as_str())
+        .or_else(|| error.get("type").and_then(|t| t.as_str()))
+        .unwrap_or_default();
+    let message = error
+        .get("message")
+        .and_then(|m| m.as_str())
+        .unwrap_or_default();
+    let category = ErrorCategory::classify_upstream(status, code, message);
+    error.insert(
+        "category".to_string(),
+        serde_json::Value::String(category.as_str().to_string()),
+    );
+    let body = serde_json::to_vec(&json).unwrap_or_else(|_| body.to_vec());
+    (category, body)
+}
+
 /// Trait for parsing provider-specific error responses.
 ///
 /// Implement this trait for each provider to extract error information
@@ -503,6 +729,19 @@ mod tests {
         assert_eq!(parsed.error.message, "Model not found");
         assert_eq!(parsed.error.error_type, "invalid_request_error");
         assert_eq!(parsed.error.code, "modelnotfoundexception"); // lowercased
+        assert_eq!(parsed.error.category.as_deref(), Some("not_found"));
+    }
+
+    #[test]
+    fn test_build_provider_error_response_category_header() {
+        let info = ProviderErrorInfo::api("Overloaded", "overloaded_error");
+        let response =
+            build_provider_error_response(StatusCode::from_u16(529).unwrap(), info).unwrap();
+
+        assert_eq!(
+            response.headers().get(ERROR_CATEGORY_HEADER).unwrap(),
+            "provider_unavailable"
+        );
     }
 
     #[tokio::test]
@@ -533,6 +772,102 @@ mod tests {
         }
     }
 
+    // ========================================================================
+    // Error Category Tests
+    // ========================================================================
+
+    #[test]
+    fn test_error_category_native_codes() {
+        let cases = [
+            ("context_length_exceeded", ErrorCategory::ContextLength),
+            ("insufficient_quota", ErrorCategory::QuotaExceeded),
+            ("content_filter", ErrorCategory::ContentFiltered),
+            ("overloaded_error", ErrorCategory::ProviderUnavailable),
+            ("permission_error", ErrorCategory::PermissionDenied),
+            ("ThrottlingException", ErrorCategory::RateLimited),
+            ("ModelTimeoutException", ErrorCategory::Timeout),
+            ("RESOURCE_EXHAUSTED", ErrorCategory::RateLimited),
+            ("UNAUTHENTICATED", ErrorCategory::Authentication),
+            ("budget_exceeded", ErrorCategory::BudgetExceeded),
+            ("circuit_breaker_open", ErrorCategory::ProviderUnavailable),
+            ("guardrails_blocked", ErrorCategory::ContentFiltered),
+        ];
+        for (code, expected) in cases {
+            // Status deliberately disagrees, to show the code wins
+            assert_eq!(
+                ErrorCategory::classify(StatusCode::BAD_REQUEST, code, ""),
+                expected,
+                "code {code}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_error_category_message_fallback() {
+        assert_eq!(
+            ErrorCategory::classify(
+                StatusCode::BAD_REQUEST,
+                "invalid_request_error",
+                "prompt is too long: 210000 tokens > 200000 maximum"
+            ),
+            ErrorCategory::ContextLength
+        );
+        assert_eq!(
+            ErrorCategory::classify(
+                StatusCode::BAD_REQUEST,
+                "ValidationException",
+                "Input was blocked by the safety filter"
+            ),
+            ErrorCategory::ContentFiltered
+        );
+    }
+
+    #[test]
+    fn test_error_category_status_fallback() {
+        let cases = [
+            (401, ErrorCategory::Authentication),
+            (402, ErrorCategory::BudgetExceeded),
+            (403, ErrorCategory::PermissionDenied),
+            (404, ErrorCategory::NotFound),
+            (413, ErrorCategory::ContextLength),
+            (422, ErrorCategory::InvalidRequest),
+            (429, ErrorCategory::RateLimited),
+            (503, ErrorCategory::ProviderUnavailable),
+            (504, ErrorCategory::Timeout),
+        ];
+        for (status, expected) in cases {
+            let status = StatusCode::from_u16(status).unwrap();
+            assert_eq!(ErrorCategory::classify(status, "", ""), expected);
+        }
+
+        // Unknown 5xx is internal when raised by the gateway, a provider
+        // error when it came from upstream
+        assert_eq!(
+            ErrorCategory::classify(StatusCode::INTERNAL_SERVER_ERROR, "", ""),
+            ErrorCategory::Internal
+        );
+        assert_eq!(
+            ErrorCategory::classify_upstream(StatusCode::INTERNAL_SERVER_ERROR, "", ""),
+            ErrorCategory::ProviderError
+        );
+    }
+
+    #[test]
+    fn test_categorize_passthrough_error() {
+        // OpenAI reports quota errors with a null code and the reason in `type`
+        let body = br#"{"error":{"message":"You exceeded your current quota","type":"insufficient_quota","code":null}}"#;
+        let (category, body) = categorize_passthrough_error(StatusCode::TOO_MANY_REQUESTS, body);
+        assert_eq!(category, ErrorCategory::QuotaExceeded);
+
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["error"]["category"], "quota_exceeded");
+        assert_eq!(json["error"]["message"], "You exceeded your current quota");
+
+        let (category, body) = categorize_passthrough_error(StatusCode::BAD_GATEWAY, b"upstream");
+        assert_eq!(category, ErrorCategory::ProviderUnavailable);
+        assert_eq!(body, b"upstream");
+    }
+
     // ========================================================================
     // Bedrock Parser - Complete Error Type Coverage
     // ========================================================================
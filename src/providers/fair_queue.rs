@@ -0,0 +1,690 @@
+//! Weighted fair queuing across tenants for providers at capacity.
+//!
+//! A provider with a `fair_queue` block caps the requests it has in flight.
+//! Below the cap requests pass straight through. At the cap they wait in
+//! per-tenant queues, and each slot that frees up goes to the queued request
+//! with the smallest virtual finish time. A tenant's finish time advances by
+//! `1 / weight` for every request it queues, starting from the current
+//! virtual time if it has nothing queued, so:
+//!
+//! - a tenant that floods the queue only pushes back its own requests;
+//! - a tenant with a single queued request is served within one round;
+//! - an idle tenant does not bank credit to burst with later.
+//!
+//! Each request also has a [`RequestPriority`]: its API key's tier, or lower
+//! if the client asked for less with `x-priority`. Freed slots always go to
+//! the highest priority with requests queued, and fair sharing applies
+//! between the tenants queued at that priority. Low priority requests can be
+//! starved while higher ones keep the provider busy; they fail once
+//! `queue_timeout_ms` runs out.
+//!
+//! Permits are released on drop. For streaming responses the permit is moved
+//! into the response body with [`hold_permit`] so the slot stays taken until
+//! the stream ends.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use axum::{
+    body::{Body, HttpBody},
+    response::Response,
+};
+use futures_util::StreamExt;
+use http::StatusCode;
+use thiserror::Error;
+use tokio::sync::oneshot;
+use uuid::Uuid;
+
+use crate::{
+    compat::{Mutex, RwLock},
+    config::{FairQueueConfig, FairQueueKey, ProvidersConfig, RequestPriority},
+    observability::metrics,
+};
+
+/// Queue key for requests with neither an org nor a project.
+const ANONYMOUS_TENANT: &str = "anonymous";
+
+/// Who a provider request is made on behalf of.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Tenant {
+    pub org_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+    /// API key the request was made with, which sets its priority tier.
+    pub api_key_id: Option<Uuid>,
+    /// Priority the client asked for (`x-priority`), capped at the tier.
+    pub priority: Option<RequestPriority>,
+}
+
+impl Tenant {
+    /// The queue key for this tenant at the given granularity.
+    fn key(&self, key: FairQueueKey) -> String {
+        let id = match key {
+            FairQueueKey::Org => self.org_id,
+            FairQueueKey::Project => self.project_id.or(self.org_id),
+        };
+        id.map(|id| id.to_string())
+            .unwrap_or_else(|| ANONYMOUS_TENANT.to_string())
+    }
+
+    /// The priority this request queues at.
+    fn priority(&self, config: &FairQueueConfig) -> RequestPriority {
+        let tier = match self.api_key_id {
+            Some(id) => config.tier_for(&id.to_string()),
+            None => config.default_priority,
+        };
+        self.priority.map_or(tier, |requested| requested.min(tier))
+    }
+}
+
+/// Error returned when a request can't get a slot with the provider.
+#[derive(Debug, Error)]
+pub enum FairQueueError {
+    #[error(
+        "Provider '{provider}' is at capacity and the request waited {waited_ms}ms without getting a slot"
+    )]
+    Timeout {
+        provider: Arc<str>,
+        waited_ms: u64,
+        status: StatusCode,
+    },
+    #[error("Provider '{provider}' is at capacity and its queue is full")]
+    QueueFull {
+        provider: Arc<str>,
+        status: StatusCode,
+    },
+}
+
+impl FairQueueError {
+    /// Status to answer with, per the queue's `reject_status`.
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Self::Timeout { status, .. } | Self::QueueFull { status, .. } => *status,
+        }
+    }
+}
+
+/// Tenant queues are kept per priority.
+type QueueKey = (RequestPriority, String);
+
+struct Waiter {
+    id: u64,
+    priority: RequestPriority,
+    finish: f64,
+    notify: oneshot::Sender<()>,
+}
+
+#[derive(Default)]
+struct TenantQueue {
+    /// Virtual finish time of the tenant's most recently queued request.
+    last_finish: f64,
+    waiters: VecDeque<Waiter>,
+}
+
+#[derive(Default)]
+struct QueueState {
+    in_flight: u32,
+    queued: usize,
+    /// Queued requests per priority, indexed by `RequestPriority as usize`.
+    queued_by_priority: [usize; RequestPriority::ALL.len()],
+    /// Finish time of the most recently dispatched request.
+    virtual_time: f64,
+    next_id: u64,
+    tenants: HashMap<QueueKey, TenantQueue>,
+}
+
+impl QueueState {
+    /// Pop the request with the smallest finish time among those queued at
+    /// the highest priority.
+    fn pop_next(&mut self) -> Option<Waiter> {
+        let priority = RequestPriority::ALL
+            .into_iter()
+            .rev()
+            .find(|p| self.queued_by_priority[*p as usize] > 0)?;
+        let tenant = self
+            .tenants
+            .iter()
+            .filter(|((p, _), _)| *p == priority)
+            .filter_map(|(key, q)| q.waiters.front().map(|w| (key, w.finish, w.id)))
+            // Ties go to the earlier request
+            .min_by(|a, b| a.1.total_cmp(&b.1).then(a.2.cmp(&b.2)))
+            .map(|(key, _, _)| key.clone())?;
+        let queue = self.tenants.get_mut(&tenant)?;
+        let waiter = queue.waiters.pop_front()?;
+        if queue.waiters.is_empty() {
+            self.tenants.remove(&tenant);
+        }
+        self.queued -= 1;
+        self.queued_by_priority[priority as usize] -= 1;
+        self.virtual_time = self.virtual_time.max(waiter.finish);
+        Some(waiter)
+    }
+
+    /// Remove a waiter that gave up. Returns false if it was already
+    /// dispatched, in which case it owns a slot.
+    fn remove(&mut self, tenant: &QueueKey, id: u64) -> bool {
+        let Some(queue) = self.tenants.get_mut(tenant) else {
+            return false;
+        };
+        let Some(pos) = queue.waiters.iter().position(|w| w.id == id) else {
+            return false;
+        };
+        queue.waiters.remove(pos);
+        if queue.waiters.is_empty() {
+            self.tenants.remove(tenant);
+        }
+        self.queued -= 1;
+        self.queued_by_priority[tenant.0 as usize] -= 1;
+        true
+    }
+}
+
+/// Concurrency limiter for one provider that shares slots fairly between
+/// tenants once the provider is at capacity.
+pub struct FairQueue {
+    provider: Arc<str>,
+    config: FairQueueConfig,
+    state: Mutex<QueueState>,
+}
+
+impl FairQueue {
+    pub fn new(provider: &str, config: &FairQueueConfig) -> Self {
+        Self {
+            provider: Arc::from(provider),
+            config: config.clone(),
+            state: Mutex::new(QueueState::default()),
+        }
+    }
+
+    /// Wait for a slot with the provider on behalf of `tenant`.
+    pub async fn acquire(
+        self: &Arc<Self>,
+        tenant: &Tenant,
+    ) -> Result<FairQueuePermit, FairQueueError> {
+        let key = (tenant.priority(&self.config), tenant.key(self.config.key));
+        let priority = key.0.as_str();
+        let started = Instant::now();
+
+        let (id, notified) = {
+            let mut state = self.state.lock();
+            if state.in_flight < self.config.max_concurrent && state.queued == 0 {
+                state.in_flight += 1;
+                drop(state);
+                metrics::record_fair_queue_wait(&self.provider, priority, "immediate", 0.0);
+                return Ok(self.permit());
+            }
+            if state.queued >= self.config.max_queue_depth {
+                drop(state);
+                metrics::record_fair_queue_wait(&self.provider, priority, "rejected", 0.0);
+                return Err(FairQueueError::QueueFull {
+                    provider: self.provider.clone(),
+                    status: self.reject_status(),
+                });
+            }
+
+            let weight = self.config.weight_for(&key.1).max(1);
+            let virtual_time = state.virtual_time;
+            let id = state.next_id;
+            state.next_id += 1;
+            let (notify, notified) = oneshot::channel();
+            let queue = state.tenants.entry(key.clone()).or_default();
+            let finish = queue.last_finish.max(virtual_time) + 1.0 / f64::from(weight);
+            queue.last_finish = finish;
+            queue.waiters.push_back(Waiter {
+                id,
+                priority: key.0,
+                finish,
+                notify,
+            });
+            state.queued += 1;
+            state.queued_by_priority[key.0 as usize] += 1;
+            self.record_depth(&state, key.0);
+            (id, notified)
+        };
+
+        let mut guard = WaitGuard {
+            queue: self,
+            tenant: &key,
+            id,
+            armed: true,
+        };
+        let timeout = Duration::from_millis(self.config.queue_timeout_ms);
+        let waited = match tokio::time::timeout(timeout, notified).await {
+            Ok(Ok(())) => {
+                guard.armed = false;
+                metrics::record_fair_queue_wait(
+                    &self.provider,
+                    priority,
+                    "granted",
+                    started.elapsed().as_secs_f64(),
+                );
+                return Ok(self.permit());
+            }
+            _ => started.elapsed(),
+        };
+        drop(guard);
+
+        tracing::warn!(
+            provider = %self.provider,
+            tenant = %key.1,
+            priority,
+            waited_ms = waited.as_millis() as u64,
+            "Timed out waiting for a provider slot"
+        );
+        metrics::record_fair_queue_wait(&self.provider, priority, "timeout", waited.as_secs_f64());
+        Err(FairQueueError::Timeout {
+            provider: self.provider.clone(),
+            waited_ms: waited.as_millis() as u64,
+            status: self.reject_status(),
+        })
+    }
+
+    fn reject_status(&self) -> StatusCode {
+        StatusCode::from_u16(self.config.reject_status).unwrap_or(StatusCode::SERVICE_UNAVAILABLE)
+    }
+
+    fn record_depth(&self, state: &QueueState, priority: RequestPriority) {
+        metrics::record_fair_queue_depth(
+            &self.provider,
+            priority.as_str(),
+            state.queued_by_priority[priority as usize],
+        );
+    }
+
+    fn permit(self: &Arc<Self>) -> FairQueuePermit {
+        FairQueuePermit {
+            queue: Arc::clone(self),
+        }
+    }
+
+    /// Hand a freed slot to the next queued request, or return it.
+    fn release(&self) {
+        let mut state = self.state.lock();
+        match state.pop_next() {
+            // The slot passes to the waiter; if it has already given up, its
+            // `WaitGuard` finds it gone from the queue and releases again.
+            Some(waiter) => {
+                let _ = waiter.notify.send(());
+                self.record_depth(&state, waiter.priority);
+            }
+            None => state.in_flight = state.in_flight.saturating_sub(1),
+        }
+    }
+
+    /// Requests currently holding a slot.
+    pub fn in_flight(&self) -> u32 {
+        self.state.lock().in_flight
+    }
+
+    /// Requests currently waiting for a slot.
+    pub fn queued(&self) -> usize {
+        self.state.lock().queued
+    }
+}
+
+/// Cleans up after a waiter that stopped waiting, whether it timed out or
+/// its future was dropped (e.g. the client disconnected).
+struct WaitGuard<'a> {
+    queue: &'a FairQueue,
+    tenant: &'a QueueKey,
+    id: u64,
+    armed: bool,
+}
+
+impl Drop for WaitGuard<'_> {
+    fn drop(&mut self) {
+        if !self.armed {
+            return;
+        }
+        let removed = {
+            let mut state = self.queue.state.lock();
+            let removed = state.remove(self.tenant, self.id);
+            if removed {
+                self.queue.record_depth(&state, self.tenant.0);
+            }
+            removed
+        };
+        if !removed {
+            // Dispatched between the timeout firing and now: pass it on
+            self.queue.release();
+        }
+    }
+}
+
+/// A slot with a provider. Dropping it lets the next queued request through.
+pub struct FairQueuePermit {
+    queue: Arc<FairQueue>,
+}
+
+impl Drop for FairQueuePermit {
+    fn drop(&mut self) {
+        self.queue.release();
+    }
+}
+
+/// Keep `permit` until the response body is finished.
+///
+/// Buffered bodies are already complete, so the permit is released straight
+/// away; streaming bodies carry it until the last chunk is read or the
+/// client goes away. Any guard works as the permit, e.g. a fair queue slot
+/// together with an adaptive concurrency slot.
+pub fn hold_permit<P: Send + 'static>(response: Response, permit: P) -> Response {
+    if response.body().size_hint().exact().is_some() {
+        return response;
+    }
+    let (parts, body) = response.into_parts();
+    let stream = body.into_data_stream().map(move |chunk| {
+        let _permit = &permit;
+        chunk
+    });
+    Response::from_parts(parts, Body::from_stream(stream))
+}
+
+/// Registry of fair queues keyed by provider name.
+#[derive(Clone, Default)]
+pub struct FairQueueRegistry {
+    queues: Arc<RwLock<HashMap<String, Arc<FairQueue>>>>,
+}
+
+impl FairQueueRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry with a queue for every provider that configures one.
+    pub fn from_config(providers: &ProvidersConfig) -> Self {
+        let registry = Self::new();
+        {
+            let mut queues = registry.queues.write();
+            for (name, config) in providers.iter() {
+                if let Some(fq) = config.fair_queue_config() {
+                    queues.insert(name.to_string(), Arc::new(FairQueue::new(name, fq)));
+                }
+            }
+        }
+        registry
+    }
+
+    /// Get the queue for a provider, creating it if the provider configures
+    /// one but wasn't known at startup (e.g. dynamic providers).
+    pub fn get_or_create(
+        &self,
+        provider_name: &str,
+        config: Option<&FairQueueConfig>,
+    ) -> Option<Arc<FairQueue>> {
+        let config = config?;
+        if let Some(queue) = self.queues.read().get(provider_name) {
+            return Some(queue.clone());
+        }
+        let mut queues = self.queues.write();
+        Some(
+            queues
+                .entry(provider_name.to_string())
+                .or_insert_with(|| Arc::new(FairQueue::new(provider_name, config)))
+                .clone(),
+        )
+    }
+
+    /// Drop a provider's queue so the next request builds a new one from its
+    /// current config. Requests holding or waiting for a slot in the old
+    /// queue keep it until they finish.
+    pub fn remove(&self, provider_name: &str) {
+        self.queues.write().remove(provider_name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(max_concurrent: u32) -> FairQueueConfig {
+        FairQueueConfig {
+            max_concurrent,
+            queue_timeout_ms: 5_000,
+            max_queue_depth: 100,
+            key: FairQueueKey::Org,
+            default_weight: 1,
+            weights: HashMap::new(),
+            default_priority: RequestPriority::Normal,
+            priority_tiers: HashMap::new(),
+            reject_status: 503,
+        }
+    }
+
+    fn org(n: u128) -> Tenant {
+        Tenant {
+            org_id: Some(Uuid::from_u128(n)),
+            ..Default::default()
+        }
+    }
+
+    /// Queue requests for each tenant behind a held slot, then release the
+    /// slot repeatedly and record which tenant each freed slot went to.
+    async fn dispatch_order(
+        queue: Arc<FairQueue>,
+        requests: Vec<(Tenant, usize)>,
+    ) -> Vec<Option<Uuid>> {
+        let held = queue.acquire(&org(0)).await.unwrap();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut handles = Vec::new();
+        for (tenant, count) in requests {
+            for _ in 0..count {
+                let queue = queue.clone();
+                let tx = tx.clone();
+                handles.push(tokio::spawn(async move {
+                    let permit = queue.acquire(&tenant).await.unwrap();
+                    tx.send((tenant.org_id, permit)).unwrap();
+                }));
+                // Let each request enqueue before the next one
+                tokio::task::yield_now().await;
+            }
+        }
+        drop(tx);
+
+        drop(held);
+        let mut order = Vec::new();
+        while let Some((org_id, permit)) = rx.recv().await {
+            order.push(org_id);
+            drop(permit);
+        }
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        order
+    }
+
+    #[tokio::test]
+    async fn test_passes_through_below_capacity() {
+        let queue = Arc::new(FairQueue::new("p", &config(2)));
+        let a = queue.acquire(&org(1)).await.unwrap();
+        let b = queue.acquire(&org(1)).await.unwrap();
+        assert_eq!(queue.in_flight(), 2);
+        drop(a);
+        drop(b);
+        assert_eq!(queue.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_heavy_tenant_does_not_starve_others() {
+        let queue = Arc::new(FairQueue::new("p", &config(1)));
+        let heavy = org(1);
+        let light = org(2);
+        let order = dispatch_order(queue.clone(), vec![(heavy, 6), (light, 2)]).await;
+
+        // FIFO would serve all six heavy requests first; fair queuing
+        // interleaves the light tenant's requests near the front
+        let light_positions: Vec<usize> = order
+            .iter()
+            .enumerate()
+            .filter(|(_, id)| **id == light.org_id)
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(light_positions, vec![1, 3]);
+        assert_eq!(queue.in_flight(), 0);
+        assert_eq!(queue.queued(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_weights_share_slots_proportionally() {
+        let mut cfg = config(1);
+        let big = org(1);
+        let small = org(2);
+        cfg.weights.insert(big.org_id.unwrap().to_string(), 3);
+        let queue = Arc::new(FairQueue::new("p", &cfg));
+        let order = dispatch_order(queue, vec![(big, 6), (small, 6)]).await;
+
+        // Among the first eight slots, the weight-3 tenant gets six
+        let big_share = order[..8].iter().filter(|id| **id == big.org_id).count();
+        assert_eq!(big_share, 6);
+    }
+
+    #[tokio::test]
+    async fn test_higher_priority_served_first() {
+        let mut cfg = config(1);
+        let vip_key = Uuid::from_u128(10);
+        cfg.priority_tiers
+            .insert(vip_key.to_string(), RequestPriority::High);
+        let queue = Arc::new(FairQueue::new("p", &cfg));
+
+        let batch = org(1);
+        let vip = Tenant {
+            api_key_id: Some(vip_key),
+            ..org(2)
+        };
+        let background = Tenant {
+            priority: Some(RequestPriority::Low),
+            ..org(3)
+        };
+        let order = dispatch_order(queue, vec![(background, 2), (batch, 2), (vip, 2)]).await;
+
+        assert_eq!(
+            order,
+            vec![
+                vip.org_id,
+                vip.org_id,
+                batch.org_id,
+                batch.org_id,
+                background.org_id,
+                background.org_id,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_requested_priority_capped_at_tier() {
+        let mut cfg = config(1);
+        let key = Uuid::from_u128(10);
+        cfg.priority_tiers
+            .insert(key.to_string(), RequestPriority::High);
+
+        let tenant = |api_key_id, priority| Tenant {
+            api_key_id,
+            priority,
+            ..Default::default()
+        };
+        assert_eq!(
+            tenant(Some(key), None).priority(&cfg),
+            RequestPriority::High
+        );
+        assert_eq!(
+            tenant(Some(key), Some(RequestPriority::Low)).priority(&cfg),
+            RequestPriority::Low
+        );
+        // Keys without a tier can't ask for more than the default
+        assert_eq!(
+            tenant(None, Some(RequestPriority::High)).priority(&cfg),
+            RequestPriority::Normal
+        );
+    }
+
+    #[tokio::test]
+    async fn test_queue_full_and_timeout() {
+        let mut cfg = config(1);
+        cfg.max_queue_depth = 1;
+        cfg.queue_timeout_ms = 20;
+        cfg.reject_status = 429;
+        let queue = Arc::new(FairQueue::new("p", &cfg));
+        let held = queue.acquire(&org(1)).await.unwrap();
+
+        let waiting = tokio::spawn({
+            let queue = queue.clone();
+            async move { queue.acquire(&org(2)).await.map(|_| ()) }
+        });
+        tokio::task::yield_now().await;
+        assert!(matches!(
+            queue.acquire(&org(3)).await,
+            Err(FairQueueError::QueueFull { .. })
+        ));
+
+        let err = waiting.await.unwrap().unwrap_err();
+        assert!(matches!(err, FairQueueError::Timeout { .. }));
+        assert_eq!(err.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(queue.queued(), 0);
+
+        drop(held);
+        assert_eq!(queue.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_waiter_leaves_queue() {
+        let queue = Arc::new(FairQueue::new("p", &config(1)));
+        let held = queue.acquire(&org(1)).await.unwrap();
+
+        let waiting = tokio::spawn({
+            let queue = queue.clone();
+            async move { queue.acquire(&org(2)).await.map(|_| ()) }
+        });
+        tokio::task::yield_now().await;
+        assert_eq!(queue.queued(), 1);
+        waiting.abort();
+        let _ = waiting.await;
+        assert_eq!(queue.queued(), 0);
+
+        drop(held);
+        assert_eq!(queue.in_flight(), 0);
+    }
+
+    #[test]
+    fn test_tenant_key() {
+        let org_id = Uuid::from_u128(1);
+        let project_id = Uuid::from_u128(2);
+        let tenant = Tenant {
+            org_id: Some(org_id),
+            project_id: Some(project_id),
+            ..Default::default()
+        };
+        assert_eq!(tenant.key(FairQueueKey::Org), org_id.to_string());
+        assert_eq!(tenant.key(FairQueueKey::Project), project_id.to_string());
+
+        let org_only = Tenant {
+            org_id: Some(org_id),
+            ..Default::default()
+        };
+        assert_eq!(org_only.key(FairQueueKey::Project), org_id.to_string());
+        assert_eq!(Tenant::default().key(FairQueueKey::Org), ANONYMOUS_TENANT);
+    }
+
+    #[tokio::test]
+    async fn test_hold_permit_on_streaming_body() {
+        let queue = Arc::new(FairQueue::new("p", &config(1)));
+
+        let buffered = Response::new(Body::from("done"));
+        let permit = queue.acquire(&org(1)).await.unwrap();
+        drop(hold_permit(buffered, permit));
+        assert_eq!(queue.in_flight(), 0);
+
+        let chunks = futures_util::stream::iter(vec![Ok::<_, std::io::Error>("a"), Ok("b")]);
+        let streaming = Response::new(Body::from_stream(chunks));
+        let permit = queue.acquire(&org(1)).await.unwrap();
+        let response = hold_permit(streaming, permit);
+        assert_eq!(queue.in_flight(), 1);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"ab");
+        assert_eq!(queue.in_flight(), 0);
+    }
+}
@@ -52,6 +52,12 @@ pub fn classify_provider_error(error: &ProviderError) -> FallbackDecision {
         // Circuit breaker open - definitely retry with fallback
         ProviderError::CircuitBreakerOpen(_) => FallbackDecision::Retry,
 
+        // Provider saturated - another provider may have capacity
+        ProviderError::AtCapacity(_) => FallbackDecision::Retry,
+
+        // Adaptive limit reached - the provider is already under load
+        ProviderError::ConcurrencyLimited(_) => FallbackDecision::Retry,
+
         // HTTP request errors - check the underlying cause
         ProviderError::Request(reqwest_err) => classify_reqwest_error(reqwest_err),
 
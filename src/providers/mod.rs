@@ -36,6 +36,7 @@
 //! enum values and other derived strings before the retry loop, as forms must be
 //! rebuilt fresh on each attempt (they are consumed when sent).
 
+pub mod adaptive_concurrency;
 pub mod anthropic;
 #[cfg(feature = "provider-bedrock")]
 pub mod aws;
@@ -45,7 +46,9 @@ pub mod azure_openai;
 pub mod bedrock;
 pub mod circuit_breaker;
 pub(crate) mod convert_utils;
+pub mod endpoints;
 pub mod error;
+pub mod fair_queue;
 pub mod fallback;
 pub mod health_check;
 pub mod image;
@@ -53,20 +56,24 @@ pub(crate) mod open_ai;
 pub mod registry;
 pub mod response;
 pub mod retry;
+pub mod settings;
 pub mod test;
 #[cfg(test)]
 pub mod test_utils;
 #[cfg(feature = "provider-vertex")]
 pub mod vertex;
 
+pub use adaptive_concurrency::AdaptiveConcurrencyRegistry;
 use async_trait::async_trait;
 use axum::{
     body::Body,
     response::{IntoResponse, Response},
 };
 use bytes::Bytes;
+pub use circuit_breaker::SharedOpenState;
+pub use fair_queue::{FairQueueRegistry, Tenant};
 pub use fallback::{
-    FallbackDecision, build_fallback_chain, classify_provider_error,
+    FallbackDecision, FallbackTarget, build_fallback_chain, classify_provider_error,
     should_fallback_on_response_status,
 };
 use http::{
@@ -75,6 +82,7 @@ use http::{
 };
 pub use registry::{CircuitBreakerRegistry, CircuitBreakerStatus};
 use serde::{Deserialize, Serialize};
+pub use settings::ProviderSettingsRegistry;
 use thiserror::Error;
 #[cfg(feature = "server")]
 use tokio_util::task::TaskTracker;
@@ -82,8 +90,8 @@ use tokio_util::task::TaskTracker;
 use crate::{
     api_types::{
         CreateChatCompletionPayload, CreateCompletionPayload, CreateEmbeddingPayload,
-        CreateImageRequest, CreateResponsesPayload, CreateSpeechRequest,
-        CreateTranscriptionRequest, CreateTranslationRequest,
+        CreateFineTuningJobRequest, CreateImageRequest, CreateResponsesPayload,
+        CreateSpeechRequest, CreateTranscriptionRequest, CreateTranslationRequest, FineTuningJob,
         images::{CreateImageEditRequest, CreateImageVariationRequest, ImagesResponse},
     },
     config::{ResponseValidationConfig, ResponseValidationMode},
@@ -177,6 +185,14 @@ pub enum ProviderError {
 
     #[error("{0}")]
     CircuitBreakerOpen(#[from] circuit_breaker::CircuitBreakerError),
+
+    /// The provider's fair queue had no slot for this request in time.
+    #[error("{0}")]
+    AtCapacity(#[from] fair_queue::FairQueueError),
+
+    /// The provider's adaptive concurrency limit had no slot in time.
+    #[error("{0}")]
+    ConcurrencyLimited(#[from] adaptive_concurrency::ConcurrencyLimitError),
 }
 
 impl From<ProviderError> for StatusCode {
@@ -188,17 +204,21 @@ impl From<ProviderError> for StatusCode {
             }
             ProviderError::Unsupported(_) => StatusCode::NOT_IMPLEMENTED,
             ProviderError::BadRequest(_, _) => StatusCode::BAD_REQUEST,
-            ProviderError::CircuitBreakerOpen(_) => StatusCode::SERVICE_UNAVAILABLE,
+            ProviderError::AtCapacity(e) => e.status(),
+            ProviderError::CircuitBreakerOpen(_) | ProviderError::ConcurrencyLimited(_) => {
+                StatusCode::SERVICE_UNAVAILABLE
+            }
         }
     }
 }
 
 impl IntoResponse for ProviderError {
     fn into_response(self) -> Response {
-        // CircuitBreakerOpen is a curated message we own (no upstream detail
-        // mixed in), so it's safe to expose. The other variants wrap reqwest
-        // / http / arbitrary internal strings that may include hostnames,
-        // file paths, or stack-trace fragments — keep those in logs only.
+        // CircuitBreakerOpen, AtCapacity and ConcurrencyLimited are curated messages we own (no
+        // upstream detail mixed in), so they're safe to expose. The other
+        // variants wrap reqwest / http / arbitrary internal strings that may
+        // include hostnames, file paths, or stack-trace fragments — keep
+        // those in logs only.
         let (status, error_code, public_message) = match &self {
             ProviderError::Request(_) => (
                 StatusCode::BAD_GATEWAY,
@@ -225,6 +245,12 @@ impl IntoResponse for ProviderError {
                 "circuit_breaker_open",
                 e.to_string(),
             ),
+            ProviderError::AtCapacity(e) => (e.status(), "provider_at_capacity", e.to_string()),
+            ProviderError::ConcurrencyLimited(e) => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "provider_at_capacity",
+                e.to_string(),
+            ),
         };
 
         tracing::error!(
@@ -238,7 +264,13 @@ impl IntoResponse for ProviderError {
         // This counter provides unified error categorization across all error types
         metrics::record_gateway_error("provider_error", error_code, None);
 
-        (status, public_message).into_response()
+        let error_type = if status.is_server_error() {
+            "server_error"
+        } else {
+            "invalid_request_error"
+        };
+        let body = crate::openapi::ErrorResponse::with_type(error_type, error_code, public_message);
+        (status, axum::Json(body)).into_response()
     }
 }
 
@@ -302,6 +334,19 @@ pub trait Provider: Send + Sync {
 
     async fn list_models(&self, client: &reqwest::Client) -> Result<ModelsResponse, ProviderError>;
 
+    /// Count the input tokens of a chat completion request with the
+    /// provider's own tokenizer. Only Anthropic exposes a counting endpoint;
+    /// the default returns `Unsupported`.
+    async fn count_tokens(
+        &self,
+        _client: &reqwest::Client,
+        _payload: CreateChatCompletionPayload,
+    ) -> Result<u64, ProviderError> {
+        Err(ProviderError::Unsupported(
+            "token counting is not supported by this provider".to_string(),
+        ))
+    }
+
     // =========================================================================
     // Image generation methods
     // =========================================================================
@@ -404,6 +449,76 @@ pub trait Provider: Send + Sync {
         ))
     }
 
+    // =========================================================================
+    // File methods
+    // =========================================================================
+    // Copies of gateway files stored at the provider, for endpoints that take
+    // provider file IDs. Only OpenAI-compatible providers implement these; the
+    // defaults return `Unsupported`, which the files endpoints surface as a 501.
+
+    /// Upload a file with the given purpose, returning the provider's file ID.
+    async fn upload_file(
+        &self,
+        _client: &reqwest::Client,
+        _file: Bytes,
+        _filename: String,
+        _purpose: &str,
+    ) -> Result<String, ProviderError> {
+        Err(ProviderError::Unsupported(
+            "file uploads are not supported by this provider".to_string(),
+        ))
+    }
+
+    /// Delete a file by the provider's file ID.
+    async fn delete_file(
+        &self,
+        _client: &reqwest::Client,
+        _file_id: &str,
+    ) -> Result<(), ProviderError> {
+        Err(ProviderError::Unsupported(
+            "file uploads are not supported by this provider".to_string(),
+        ))
+    }
+
+    // =========================================================================
+    // Fine-tuning methods
+    // =========================================================================
+    // Only OpenAI-compatible providers implement these; the defaults return
+    // `Unsupported`, which the fine-tuning endpoints surface as a 501.
+
+    /// Start a fine-tuning job. File IDs in the payload are the provider's.
+    async fn create_fine_tuning_job(
+        &self,
+        _client: &reqwest::Client,
+        _payload: CreateFineTuningJobRequest,
+    ) -> Result<FineTuningJob, ProviderError> {
+        Err(ProviderError::Unsupported(
+            "fine-tuning is not supported by this provider".to_string(),
+        ))
+    }
+
+    /// Fetch a fine-tuning job by the provider's job ID.
+    async fn get_fine_tuning_job(
+        &self,
+        _client: &reqwest::Client,
+        _job_id: &str,
+    ) -> Result<FineTuningJob, ProviderError> {
+        Err(ProviderError::Unsupported(
+            "fine-tuning is not supported by this provider".to_string(),
+        ))
+    }
+
+    /// Cancel a fine-tuning job by the provider's job ID.
+    async fn cancel_fine_tuning_job(
+        &self,
+        _client: &reqwest::Client,
+        _job_id: &str,
+    ) -> Result<FineTuningJob, ProviderError> {
+        Err(ProviderError::Unsupported(
+            "fine-tuning is not supported by this provider".to_string(),
+        ))
+    }
+
     // =========================================================================
     // Health check methods
     // =========================================================================
@@ -446,8 +561,6 @@ pub trait Provider: Send + Sync {
                 }
             }
             ProviderHealthCheckMode::Inference => {
-                use crate::api_types::chat_completion::{Message, MessageContent};
-
                 let model = match config
                     .model
                     .as_deref()
@@ -467,34 +580,8 @@ pub trait Provider: Send + Sync {
                 };
                 let prompt = config.prompt();
 
-                let payload = CreateChatCompletionPayload {
-                    messages: vec![Message::User {
-                        content: MessageContent::Text(prompt.to_string()),
-                        name: None,
-                    }],
-                    model: Some(model.to_string()),
-                    models: None,
-                    max_tokens: Some(5), // Minimal tokens to reduce cost
-                    max_completion_tokens: None,
-                    temperature: None,
-                    top_p: None,
-                    stream: false,
-                    stop: None,
-                    presence_penalty: None,
-                    frequency_penalty: None,
-                    logit_bias: None,
-                    user: None,
-                    seed: None,
-                    tools: None,
-                    tool_choice: None,
-                    response_format: None,
-                    logprobs: None,
-                    top_logprobs: None,
-                    stream_options: None,
-                    metadata: None,
-                    reasoning: None,
-                    sovereignty_requirements: None,
-                };
+                // Minimal tokens to reduce cost
+                let payload = health_check_payload(model, prompt, 5);
 
                 match self.create_chat_completion(client, payload).await {
                     Ok(response) => {
@@ -520,6 +607,45 @@ pub trait Provider: Send + Sync {
     }
 }
 
+/// Build the single-message, non-streaming completion request used by
+/// inference health checks and provider probes.
+pub(crate) fn health_check_payload(
+    model: &str,
+    prompt: &str,
+    max_tokens: u64,
+) -> CreateChatCompletionPayload {
+    use crate::api_types::chat_completion::{Message, MessageContent};
+
+    CreateChatCompletionPayload {
+        messages: vec![Message::User {
+            content: MessageContent::Text(prompt.to_string()),
+            name: None,
+        }],
+        model: Some(model.to_string()),
+        models: None,
+        max_tokens: Some(max_tokens),
+        max_completion_tokens: None,
+        temperature: None,
+        top_p: None,
+        stream: false,
+        stop: None,
+        presence_penalty: None,
+        frequency_penalty: None,
+        logit_bias: None,
+        user: None,
+        seed: None,
+        tools: None,
+        tool_choice: None,
+        response_format: None,
+        logprobs: None,
+        top_logprobs: None,
+        stream_options: None,
+        metadata: None,
+        reasoning: None,
+        sovereignty_requirements: None,
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelsResponse {
     pub data: Vec<ModelInfo>,
@@ -598,6 +724,19 @@ async fn build_response(
 ) -> Result<Response, ProviderError> {
     let status = response.status();
 
+    // Errors are JSON even for streaming requests; buffer them so they can be
+    // tagged with a category like errors from translated providers.
+    if !status.is_success() {
+        let body = response.bytes().await?;
+        let (category, body) = error::categorize_passthrough_error(status, &body);
+        return Response::builder()
+            .status(status)
+            .header(CONTENT_TYPE, "application/json")
+            .header(error::ERROR_CATEGORY_HEADER, category.as_str())
+            .body(Body::from(body))
+            .map_err(ProviderError::ResponseBuilder);
+    }
+
     if stream {
         #[cfg(not(target_arch = "wasm32"))]
         let byte_stream = response.bytes_stream();
@@ -675,6 +814,9 @@ pub async fn inject_cost_into_response(params: CostInjectionParams<'_>) -> Respo
                 use futures_util::StreamExt;
 
                 let (parts, body) = response.into_parts();
+                let mut entry = entry;
+                (entry.provider_attempts, entry.suspected_duplicate_attempts) =
+                    retry::ProviderAttempts::usage_fields(&parts.headers);
 
                 // Convert body to byte stream with proper type annotations
                 let stream = body.into_data_stream().map(
@@ -822,15 +964,20 @@ pub async fn inject_cost_into_response(params: CostInjectionParams<'_>) -> Respo
                 .and_then(as_int)
                 .unwrap_or(0);
 
-            // Extract cached tokens from input_tokens_details or prompt_tokens_details
-            let cached = usage
-                .and_then(|u| {
-                    u.get("input_tokens_details")
-                        .or_else(|| u.get("prompt_tokens_details"))
-                })
+            // Extract cache reads and writes from input_tokens_details or
+            // prompt_tokens_details
+            let input_details = usage.and_then(|u| {
+                u.get("input_tokens_details")
+                    .or_else(|| u.get("prompt_tokens_details"))
+            });
+            let cached = input_details
                 .and_then(|d| d.get("cached_tokens"))
                 .and_then(as_int)
                 .unwrap_or(0);
+            let cache_write = input_details
+                .and_then(|d| d.get("cache_creation_input_tokens"))
+                .and_then(as_int)
+                .unwrap_or(0);
 
             // Extract reasoning tokens from output_tokens_details or completion_tokens_details
             let reasoning = usage
@@ -868,7 +1015,15 @@ pub async fn inject_cost_into_response(params: CostInjectionParams<'_>) -> Respo
                 });
 
             // Calculate cost in microcents
-            let cost_result = pricing.calculate_cost(provider, model, input, output);
+            let cost_result = pricing.calculate_cost_detailed(
+                provider,
+                model,
+                &crate::pricing::TokenUsage {
+                    cached_tokens: Some(cached),
+                    cache_write_tokens: Some(cache_write),
+                    ..crate::pricing::TokenUsage::new(input, output)
+                },
+            );
             let cost_microcents = cost_result.map(|(c, _)| c);
             let pricing_source = cost_result
                 .map(|(_, s)| s)
@@ -1054,6 +1209,20 @@ pub async fn log_media_usage(params: MediaUsageParams<'_>) -> (Option<i64>, bool
             tool_results_count: None,
             tool_runtime_seconds: None,
             tool_exit_code: None,
+            jwt_subject: None,
+            error_code: None,
+            structured_output_repairs: None,
+            context_original_tokens: None,
+            context_compressed_tokens: None,
+            degraded_from_model: None,
+            degradation_reason: None,
+            ttft_ms: None,
+            inter_token_latency_ms: None,
+            output_tokens_per_second: None,
+            idempotency_key: None,
+            provider_attempts: None,
+            suspected_duplicate_attempts: None,
+            tags: None,
         };
 
         let db = db_pool.clone();
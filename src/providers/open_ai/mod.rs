@@ -1,4 +1,4 @@
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{borrow::Cow, collections::HashMap, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
 use axum::{body::Body, response::Response};
@@ -10,8 +10,8 @@ use serde_json::Value;
 use crate::{
     api_types::{
         CreateChatCompletionPayload, CreateCompletionPayload, CreateEmbeddingPayload,
-        CreateImageRequest, CreateResponsesPayload, CreateSpeechRequest,
-        CreateTranscriptionRequest, CreateTranslationRequest,
+        CreateFineTuningJobRequest, CreateImageRequest, CreateResponsesPayload,
+        CreateSpeechRequest, CreateTranscriptionRequest, CreateTranslationRequest, FineTuningJob,
         audio::AudioResponseFormat,
         images::{CreateImageEditRequest, CreateImageVariationRequest, ImagesResponse},
     },
@@ -19,7 +19,8 @@ use crate::{
     providers,
     providers::{
         CircuitBreakerRegistry, ModelsResponse, Provider, ProviderError,
-        circuit_breaker::CircuitBreaker, retry::with_circuit_breaker_and_retry,
+        circuit_breaker::CircuitBreaker, endpoints::select_endpoint,
+        retry::with_circuit_breaker_and_retry,
     },
 };
 
@@ -65,7 +66,22 @@ impl OpenAICompatibleProvider {
         provider_name: &str,
         registry: &CircuitBreakerRegistry,
     ) -> Self {
-        let circuit_breaker = registry.get_or_create(provider_name, &config.circuit_breaker);
+        let (config, circuit_breaker) = if config.endpoints.is_empty() {
+            let breaker = registry.get_or_create(provider_name, &config.circuit_breaker);
+            (Cow::Borrowed(config), breaker)
+        } else {
+            let (endpoint, breaker) = select_endpoint(
+                registry,
+                provider_name,
+                config.endpoint_selection,
+                &config.endpoints,
+                &config.circuit_breaker,
+            );
+            let config = endpoint.map_or(Cow::Borrowed(config), |e| {
+                Cow::Owned(config.with_endpoint(e))
+            });
+            (config, Some(breaker))
+        };
         let base_url = config.base_url.trim_end_matches('/').to_string();
 
         let mut headers = config.headers.clone();
@@ -873,4 +889,156 @@ impl Provider for OpenAICompatibleProvider {
             .header(CONTENT_TYPE, content_type)
             .body(Body::from(bytes))?)
     }
+
+    #[tracing::instrument(
+        skip(self, client, file),
+        fields(provider = "openai", operation = "upload_file", file_size = file.len())
+    )]
+    async fn upload_file(
+        &self,
+        client: &reqwest::Client,
+        file: Bytes,
+        filename: String,
+        purpose: &str,
+    ) -> Result<String, ProviderError> {
+        let url = format!("{}/files", self.base_url);
+
+        let response = with_circuit_breaker_and_retry(
+            self.circuit_breaker.as_deref(),
+            &self.circuit_breaker_config,
+            &self.retry,
+            "openai",
+            "upload_file",
+            || {
+                // Build form fresh for each retry attempt (Form is consumed on send)
+                let form = Form::new()
+                    .part(
+                        "file",
+                        Part::bytes(file.to_vec()).file_name(filename.clone()),
+                    )
+                    .text("purpose", purpose.to_string());
+
+                let url = url.clone();
+                async move {
+                    self.build_multipart_request(client, &url, form)
+                        .send()
+                        .await
+                }
+            },
+        )
+        .await?;
+
+        let response = Self::check_response(response).await?;
+        let body: Value = response.json().await?;
+        body["id"].as_str().map(String::from).ok_or_else(|| {
+            ProviderError::Internal("OpenAI file upload response has no id".to_string())
+        })
+    }
+
+    #[tracing::instrument(
+        skip(self, client),
+        fields(provider = "openai", operation = "delete_file")
+    )]
+    async fn delete_file(
+        &self,
+        client: &reqwest::Client,
+        file_id: &str,
+    ) -> Result<(), ProviderError> {
+        let url = format!("{}/files/{}", self.base_url, file_id);
+
+        let response = with_circuit_breaker_and_retry(
+            self.circuit_breaker.as_deref(),
+            &self.circuit_breaker_config,
+            &self.retry,
+            "openai",
+            "delete_file",
+            || async { self.build_request(client.delete(&url)).send().await },
+        )
+        .await?;
+
+        Self::check_response(response).await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(
+        skip(self, client, payload),
+        fields(provider = "openai", operation = "create_fine_tuning_job", model = %payload.model)
+    )]
+    async fn create_fine_tuning_job(
+        &self,
+        client: &reqwest::Client,
+        payload: CreateFineTuningJobRequest,
+    ) -> Result<FineTuningJob, ProviderError> {
+        let url = format!("{}/fine_tuning/jobs", self.base_url);
+        let body = serde_json::to_vec(&payload).unwrap_or_default();
+
+        let response = with_circuit_breaker_and_retry(
+            self.circuit_breaker.as_deref(),
+            &self.circuit_breaker_config,
+            &self.retry.without_retries(),
+            "openai",
+            "create_fine_tuning_job",
+            || async {
+                self.build_request(client.post(&url))
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(body.clone())
+                    .send()
+                    .await
+            },
+        )
+        .await?;
+
+        let response = Self::check_response(response).await?;
+        Ok(response.json().await?)
+    }
+
+    #[tracing::instrument(
+        skip(self, client),
+        fields(provider = "openai", operation = "get_fine_tuning_job")
+    )]
+    async fn get_fine_tuning_job(
+        &self,
+        client: &reqwest::Client,
+        job_id: &str,
+    ) -> Result<FineTuningJob, ProviderError> {
+        let url = format!("{}/fine_tuning/jobs/{}", self.base_url, job_id);
+
+        let response = with_circuit_breaker_and_retry(
+            self.circuit_breaker.as_deref(),
+            &self.circuit_breaker_config,
+            &self.retry.for_read_only(),
+            "openai",
+            "get_fine_tuning_job",
+            || async { self.build_request(client.get(&url)).send().await },
+        )
+        .await?;
+
+        let response = Self::check_response(response).await?;
+        Ok(response.json().await?)
+    }
+
+    #[tracing::instrument(
+        skip(self, client),
+        fields(provider = "openai", operation = "cancel_fine_tuning_job")
+    )]
+    async fn cancel_fine_tuning_job(
+        &self,
+        client: &reqwest::Client,
+        job_id: &str,
+    ) -> Result<FineTuningJob, ProviderError> {
+        let url = format!("{}/fine_tuning/jobs/{}/cancel", self.base_url, job_id);
+
+        let response = with_circuit_breaker_and_retry(
+            self.circuit_breaker.as_deref(),
+            &self.circuit_breaker_config,
+            &self.retry,
+            "openai",
+            "cancel_fine_tuning_job",
+            || async { self.build_request(client.post(&url)).send().await },
+        )
+        .await?;
+
+        let response = Self::check_response(response).await?;
+        Ok(response.json().await?)
+    }
 }
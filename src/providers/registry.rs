@@ -2,9 +2,16 @@
 //!
 //! Circuit breakers need to persist across requests to track failures
 //! and protect against unhealthy providers. This module provides a
-//! registry that stores circuit breakers keyed by provider name.
-
-use std::{collections::HashMap, sync::Arc};
+//! registry that stores circuit breakers keyed by provider name, or by
+//! `<provider>/<endpoint>` for providers with several endpoints.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
 
 use serde::Serialize;
 
@@ -22,6 +29,11 @@ use crate::{
 #[derive(Clone, Default)]
 pub struct CircuitBreakerRegistry {
     breakers: Arc<RwLock<HashMap<String, Arc<CircuitBreaker>>>>,
+    /// Breaker keys that belong to a provider endpoint, mapped to the
+    /// provider and endpoint names.
+    endpoints: Arc<RwLock<HashMap<String, (String, String)>>>,
+    /// Per-provider request counters for rotating between endpoints.
+    endpoint_turns: Arc<RwLock<HashMap<String, Arc<AtomicUsize>>>>,
     event_bus: Option<Arc<EventBus>>,
 }
 
@@ -30,6 +42,8 @@ impl CircuitBreakerRegistry {
     pub fn new() -> Self {
         Self {
             breakers: Arc::new(RwLock::new(HashMap::new())),
+            endpoints: Arc::new(RwLock::new(HashMap::new())),
+            endpoint_turns: Arc::new(RwLock::new(HashMap::new())),
             event_bus: None,
         }
     }
@@ -56,6 +70,8 @@ impl CircuitBreakerRegistry {
     ) -> Self {
         let registry = Self {
             breakers: Arc::new(RwLock::new(HashMap::new())),
+            endpoints: Arc::new(RwLock::new(HashMap::new())),
+            endpoint_turns: Arc::new(RwLock::new(HashMap::new())),
             event_bus: Some(event_bus.clone()),
         };
 
@@ -117,24 +133,99 @@ impl CircuitBreakerRegistry {
         Some(breaker)
     }
 
+    /// Get or create the circuit breaker for one endpoint of a provider.
+    ///
+    /// Unlike [`Self::get_or_create`], the breaker is created even when
+    /// circuit breaking is disabled, since it also tracks the endpoint's
+    /// latency for endpoint selection. A disabled breaker never opens.
+    pub fn get_or_create_endpoint(
+        &self,
+        provider_name: &str,
+        endpoint: &str,
+        config: &CircuitBreakerConfig,
+    ) -> Arc<CircuitBreaker> {
+        let key = endpoint_key(provider_name, endpoint);
+        if let Some(breaker) = self.breakers.read().get(&key)
+            && self.endpoints.read().contains_key(&key)
+        {
+            return breaker.clone();
+        }
+
+        let mut breakers = self.breakers.write();
+        let breaker = breakers
+            .entry(key.clone())
+            .or_insert_with(|| {
+                Arc::new(match &self.event_bus {
+                    Some(event_bus) => {
+                        CircuitBreaker::with_event_bus(key.as_str(), config, event_bus.clone())
+                    }
+                    None => CircuitBreaker::new(key.as_str(), config),
+                })
+            })
+            .clone();
+        self.endpoints
+            .write()
+            .insert(key, (provider_name.to_string(), endpoint.to_string()));
+        breaker
+    }
+
+    /// Count a request to a provider with several endpoints, returning how
+    /// many came before it. Used to rotate between endpoints.
+    pub fn next_endpoint_turn(&self, provider_name: &str) -> usize {
+        if let Some(turns) = self.endpoint_turns.read().get(provider_name) {
+            return turns.fetch_add(1, Ordering::Relaxed);
+        }
+        self.endpoint_turns
+            .write()
+            .entry(provider_name.to_string())
+            .or_default()
+            .fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Drop a provider's circuit breaker so the next request builds a new
+    /// one from its current config. The breaker's state is lost, along with
+    /// the breakers of its endpoints.
+    pub fn remove(&self, provider_name: &str) {
+        let mut breakers = self.breakers.write();
+        breakers.remove(provider_name);
+        self.endpoints.write().retain(|key, (provider, _)| {
+            let keep = provider != provider_name;
+            if !keep {
+                breakers.remove(key);
+            }
+            keep
+        });
+        self.endpoint_turns.write().remove(provider_name);
+    }
+
     /// Get a circuit breaker by name if it exists.
     pub fn get(&self, provider_name: &str) -> Option<Arc<CircuitBreaker>> {
         let breakers = self.breakers.read();
         breakers.get(provider_name).cloned()
     }
 
+    /// Circuit breakers whose opens are shared with other nodes.
+    pub fn shared(&self) -> Vec<(String, Arc<CircuitBreaker>)> {
+        let breakers = self.breakers.read();
+        breakers
+            .iter()
+            .filter(|(_, breaker)| breaker.is_shared())
+            .map(|(name, breaker)| (name.clone(), breaker.clone()))
+            .collect()
+    }
+
     /// Get the status of all circuit breakers.
     pub fn status(&self) -> Vec<CircuitBreakerStatus> {
         let breakers = self.breakers.read();
+        let endpoints = self.endpoints.read();
         breakers
             .iter()
-            .map(
-                |(name, breaker): (&String, &Arc<CircuitBreaker>)| CircuitBreakerStatus {
-                    provider: name.clone(),
-                    state: breaker.state(),
-                    failure_count: breaker.failure_count(),
-                },
-            )
+            .map(|(key, breaker)| match endpoints.get(key) {
+                Some((provider, endpoint)) => {
+                    CircuitBreakerStatus::new(provider, Some(endpoint), breaker)
+                }
+                None => CircuitBreakerStatus::new(key, None, breaker),
+            })
             .collect()
     }
 
@@ -143,14 +234,31 @@ impl CircuitBreakerRegistry {
         let breakers = self.breakers.read();
         breakers
             .get(provider_name)
-            .map(|breaker: &Arc<CircuitBreaker>| CircuitBreakerStatus {
-                provider: provider_name.to_string(),
-                state: breaker.state(),
-                failure_count: breaker.failure_count(),
+            .map(|breaker| CircuitBreakerStatus::new(provider_name, None, breaker))
+    }
+
+    /// Get the status of each endpoint of a provider, in no particular
+    /// order. Empty for providers without endpoints.
+    pub fn endpoint_status(&self, provider_name: &str) -> Vec<CircuitBreakerStatus> {
+        let breakers = self.breakers.read();
+        self.endpoints
+            .read()
+            .iter()
+            .filter(|(_, (provider, _))| provider == provider_name)
+            .filter_map(|(key, (provider, endpoint))| {
+                breakers
+                    .get(key)
+                    .map(|breaker| CircuitBreakerStatus::new(provider, Some(endpoint), breaker))
             })
+            .collect()
     }
 }
 
+/// Registry key for a provider endpoint's circuit breaker.
+pub fn endpoint_key(provider_name: &str, endpoint: &str) -> String {
+    format!("{provider_name}/{endpoint}")
+}
+
 /// Status of a circuit breaker for API responses.
 #[derive(Debug, Clone, Serialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
@@ -163,6 +271,25 @@ pub struct CircuitBreakerStatus {
     /// Number of consecutive failures (only relevant in Closed state).
     #[cfg_attr(feature = "utoipa", schema(example = 0))]
     pub failure_count: u32,
+    /// Endpoint name, for providers with several endpoints.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    /// Moving average of recent successful response latency in
+    /// milliseconds, when any has been recorded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+}
+
+impl CircuitBreakerStatus {
+    fn new(provider: &str, endpoint: Option<&str>, breaker: &CircuitBreaker) -> Self {
+        Self {
+            provider: provider.to_string(),
+            state: breaker.state(),
+            failure_count: breaker.failure_count(),
+            endpoint: endpoint.map(String::from),
+            latency_ms: breaker.latency().map(|l| l.as_millis() as u64),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -243,4 +370,41 @@ mod tests {
         let status = registry.status_for("test").unwrap();
         assert_eq!(status.state, CircuitState::Open);
     }
+
+    #[test]
+    fn test_registry_endpoint_breakers() {
+        let registry = CircuitBreakerRegistry::new();
+
+        // Endpoint breakers exist even when circuit breaking is disabled
+        let eu = registry.get_or_create_endpoint("azure", "eu", &test_config(false));
+        let again = registry.get_or_create_endpoint("azure", "eu", &test_config(false));
+        assert!(Arc::ptr_eq(&eu, &again));
+        registry.get_or_create_endpoint("azure", "primary", &test_config(false));
+        registry.get_or_create("openai", &test_config(true));
+
+        let status = registry.endpoint_status("azure");
+        assert_eq!(status.len(), 2);
+        assert!(status.iter().all(|s| s.provider == "azure"));
+        assert!(registry.status_for("azure").is_none());
+        assert!(registry.status_for("azure/eu").is_some());
+
+        let all = registry.status();
+        let eu_status = all
+            .iter()
+            .find(|s| s.endpoint.as_deref() == Some("eu"))
+            .unwrap();
+        assert_eq!(eu_status.provider, "azure");
+
+        registry.remove("azure");
+        assert!(registry.endpoint_status("azure").is_empty());
+        assert_eq!(registry.status().len(), 1);
+    }
+
+    #[test]
+    fn test_registry_endpoint_turns() {
+        let registry = CircuitBreakerRegistry::new();
+        assert_eq!(registry.next_endpoint_turn("azure"), 0);
+        assert_eq!(registry.next_endpoint_turn("azure"), 1);
+        assert_eq!(registry.next_endpoint_turn("openai"), 0);
+    }
 }
@@ -21,14 +21,18 @@
 
 use axum::{body::Body, response::Response};
 use bytes::Bytes;
-use futures_util::Stream;
-use http::StatusCode;
+use futures_util::{Stream, StreamExt};
+use http::{
+    StatusCode,
+    header::{CONTENT_TYPE, HeaderName, TRANSFER_ENCODING},
+};
 use serde::Serialize;
 
 use super::{
     ProviderError,
     error::{ProviderErrorParser, build_provider_error_response},
 };
+use crate::{observability::metrics, streaming::ResponseSizeLimitStream};
 
 /// Build a JSON response with the standard `application/json` content type.
 ///
@@ -114,6 +118,64 @@ pub async fn error_response<P: ProviderErrorParser>(
     build_provider_error_response(status, error_info)
 }
 
+/// Record the size of a provider response and enforce the provider's
+/// `max_response_bytes` limit.
+///
+/// Buffered responses are measured directly and rejected with a 502 when they
+/// exceed the limit. Streaming responses are wrapped so bytes are counted as
+/// chunks arrive and the stream is cut off as soon as the limit is crossed,
+/// before a runaway upstream can grow gateway memory without bound.
+pub async fn limit_response_size(
+    response: Response,
+    provider: &str,
+    limit: Option<usize>,
+) -> Result<Response, ProviderError> {
+    let header_contains = |name: HeaderName, needle: &str| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.contains(needle))
+    };
+    let is_streaming = header_contains(CONTENT_TYPE, "text/event-stream")
+        || header_contains(TRANSFER_ENCODING, "chunked");
+
+    let (parts, body) = response.into_parts();
+
+    if is_streaming {
+        let stream = body
+            .into_data_stream()
+            .map(|result| result.map_err(std::io::Error::other));
+        let limited = ResponseSizeLimitStream::new(stream, provider.to_string(), limit);
+        return Ok(Response::from_parts(parts, Body::from_stream(limited)));
+    }
+
+    // Non-streaming provider responses are already buffered by the provider
+    // implementation, so reading them here costs no extra memory.
+    let bytes = axum::body::to_bytes(body, usize::MAX)
+        .await
+        .map_err(|e| ProviderError::Internal(format!("Failed to read provider response: {e}")))?;
+    metrics::record_provider_payload_bytes(provider, "response", bytes.len() as u64);
+
+    if let Some(limit) = limit
+        && bytes.len() > limit
+    {
+        tracing::warn!(
+            provider = %provider,
+            size_bytes = bytes.len(),
+            limit_bytes = limit,
+            "Provider response exceeded max_response_bytes"
+        );
+        metrics::record_provider_response_too_large(provider);
+        return Err(ProviderError::BadGateway(
+            "response_too_large",
+            "Upstream provider response exceeded the configured size limit".to_string(),
+        ));
+    }
+
+    Ok(Response::from_parts(parts, Body::from(bytes)))
+}
+
 #[cfg(test)]
 mod tests {
     use axum::body::to_bytes;
@@ -162,6 +224,49 @@ mod tests {
         assert!(body_str.contains("data: [DONE]"));
     }
 
+    #[tokio::test]
+    async fn test_limit_response_size_rejects_oversized_buffered_response() {
+        let body = json!({"data": "x".repeat(64)});
+        let response = json_response(StatusCode::OK, &body).unwrap();
+
+        let err = limit_response_size(response, "test", Some(16))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ProviderError::BadGateway("response_too_large", _)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_limit_response_size_passes_buffered_response_under_limit() {
+        let body = json!({"ok": true});
+        let response = json_response(StatusCode::OK, &body).unwrap();
+
+        let response = limit_response_size(response, "test", Some(1024))
+            .await
+            .unwrap();
+        let body_bytes = to_bytes(response.into_body(), 1024).await.unwrap();
+        assert_eq!(&body_bytes[..], br#"{"ok":true}"#);
+    }
+
+    #[tokio::test]
+    async fn test_limit_response_size_aborts_oversized_stream() {
+        let chunks: Vec<Result<Bytes, std::io::Error>> = vec![
+            Ok(Bytes::from("data: {\"test\":1}\n\n")),
+            Ok(Bytes::from("data: {\"test\":2}\n\n")),
+            Ok(Bytes::from("data: [DONE]\n\n")),
+        ];
+        let response = streaming_response(StatusCode::OK, stream::iter(chunks)).unwrap();
+
+        let response = limit_response_size(response, "test", Some(24))
+            .await
+            .unwrap();
+        let mut body = response.into_body().into_data_stream();
+        assert!(body.next().await.unwrap().is_ok());
+        assert!(body.next().await.unwrap().is_err());
+    }
+
     #[test]
     fn test_json_response_with_error_status() {
         let body = json!({"error": {"message": "Bad request"}});
@@ -6,7 +6,7 @@
 //! Also integrates with the circuit breaker pattern to prevent hammering
 //! unhealthy providers.
 
-use std::future::Future;
+use std::{cell::Cell, future::Future, time::SystemTime};
 
 use reqwest::StatusCode;
 use tracing::{debug, warn};
@@ -58,6 +58,101 @@ pub fn is_retryable_error(error: &reqwest::Error) -> bool {
             .unwrap_or(false)
 }
 
+/// Response header with the attempts sent to the provider. Also recorded in
+/// usage.
+pub const ATTEMPTS_HEADER: &str = "X-Provider-Attempts";
+/// Response header with the attempts that timed out before a retry. Also
+/// recorded in usage.
+pub const TIMED_OUT_ATTEMPTS_HEADER: &str = "X-Provider-Timed-Out-Attempts";
+
+/// Attempts [`with_retry`] made for one provider call, collected by
+/// [`track_attempts`].
+///
+/// A timed-out attempt may still have completed upstream, and been billed,
+/// after we gave up on it and retried. Usage records these as suspected
+/// duplicate charges so they can be reconciled against the provider's own
+/// usage reports.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProviderAttempts {
+    /// Requests sent, counting retries
+    pub attempts: u32,
+    /// Attempts that timed out and were retried
+    pub timed_out: u32,
+}
+
+impl ProviderAttempts {
+    /// Set [`ATTEMPTS_HEADER`] and [`TIMED_OUT_ATTEMPTS_HEADER`]. Nothing is
+    /// set when no request was sent.
+    pub fn insert_headers(&self, headers: &mut http::HeaderMap) {
+        if self.attempts == 0 {
+            return;
+        }
+        headers.insert(ATTEMPTS_HEADER, http::HeaderValue::from(self.attempts));
+        headers.insert(
+            TIMED_OUT_ATTEMPTS_HEADER,
+            http::HeaderValue::from(self.timed_out),
+        );
+    }
+
+    /// Read attempts back from the headers set by [`Self::insert_headers`].
+    pub fn from_headers(headers: &http::HeaderMap) -> Option<Self> {
+        let header = |name| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse().ok())
+        };
+        Some(Self {
+            attempts: header(ATTEMPTS_HEADER)?,
+            timed_out: header(TIMED_OUT_ATTEMPTS_HEADER).unwrap_or(0),
+        })
+    }
+
+    /// `(provider_attempts, suspected_duplicate_attempts)` for a usage log
+    /// entry, from the headers set by [`Self::insert_headers`].
+    pub fn usage_fields(headers: &http::HeaderMap) -> (Option<i32>, Option<i32>) {
+        match Self::from_headers(headers) {
+            Some(a) => (
+                Some(i32::try_from(a.attempts).unwrap_or(i32::MAX)),
+                Some(i32::try_from(a.timed_out).unwrap_or(i32::MAX)),
+            ),
+            None => (None, None),
+        }
+    }
+}
+
+tokio::task_local! {
+    static ATTEMPTS: Cell<ProviderAttempts>;
+}
+
+/// Run `future`, counting the attempts every [`with_retry`] inside it makes.
+pub async fn track_attempts<F: Future>(future: F) -> (F::Output, ProviderAttempts) {
+    ATTEMPTS
+        .scope(Cell::new(ProviderAttempts::default()), async move {
+            let output = future.await;
+            (output, ATTEMPTS.with(Cell::get))
+        })
+        .await
+}
+
+/// Count an attempt towards the enclosing [`track_attempts`], if any.
+fn record_attempt() {
+    update_attempts(|attempts| attempts.attempts += 1);
+}
+
+/// Count an attempt that timed out and is about to be retried.
+fn record_timeout() {
+    update_attempts(|attempts| attempts.timed_out += 1);
+}
+
+fn update_attempts(update: impl FnOnce(&mut ProviderAttempts)) {
+    let _ = ATTEMPTS.try_with(|cell| {
+        let mut attempts = cell.get();
+        update(&mut attempts);
+        cell.set(attempts);
+    });
+}
+
 /// Execute an async operation with retry logic.
 ///
 /// The `make_request` function is called for each attempt. It should return
@@ -76,6 +171,7 @@ where
     Fut: Future<Output = Result<reqwest::Response, reqwest::Error>>,
 {
     if !config.enabled {
+        record_attempt();
         return make_request().await;
     }
 
@@ -83,6 +179,7 @@ where
 
     for attempt in 0..max_attempts {
         let result = make_request().await;
+        record_attempt();
 
         match result {
             Ok(response) => {
@@ -119,6 +216,9 @@ where
             Err(error) => {
                 // Check if error is retryable
                 if is_retryable_error(&error) && attempt < max_attempts - 1 {
+                    if error.is_timeout() {
+                        record_timeout();
+                    }
                     let delay = config.delay_for_attempt(attempt);
                     warn!(
                         provider = provider_name,
@@ -196,6 +296,7 @@ where
     }
 
     // Execute with retry logic
+    let started = SystemTime::now();
     let result = with_retry(retry_config, provider_name, operation, make_request).await;
 
     // Record result to circuit breaker
@@ -207,6 +308,9 @@ where
                     cb.record_failure();
                 } else {
                     cb.record_success();
+                    if response.status().is_success() {
+                        cb.record_latency(started.elapsed().unwrap_or_default());
+                    }
                 }
             }
             Err(_) => {
@@ -850,4 +954,59 @@ mod tests {
         // Should not retry when disabled
         assert_eq!(attempt_count.load(std::sync::atomic::Ordering::SeqCst), 1);
     }
+
+    #[tokio::test]
+    async fn test_track_attempts_counts_timed_out_retries() {
+        use wiremock::{Mock, MockServer, ResponseTemplate, matchers::method};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_delay(std::time::Duration::from_secs(2)))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_millis(200))
+            .build()
+            .unwrap();
+        let config = RetryConfig {
+            max_retries: 2,
+            initial_delay_ms: 1,
+            ..Default::default()
+        };
+
+        let (result, attempts) = track_attempts(with_retry(&config, "test", "chat", || {
+            client.post(server.uri()).send()
+        }))
+        .await;
+
+        assert_eq!(result.unwrap().status(), StatusCode::OK);
+        assert_eq!(
+            attempts,
+            ProviderAttempts {
+                attempts: 2,
+                timed_out: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_provider_attempts_header_round_trip() {
+        let mut headers = http::HeaderMap::new();
+        ProviderAttempts::default().insert_headers(&mut headers);
+        assert!(headers.is_empty());
+        assert_eq!(ProviderAttempts::from_headers(&headers), None);
+
+        let attempts = ProviderAttempts {
+            attempts: 3,
+            timed_out: 1,
+        };
+        attempts.insert_headers(&mut headers);
+        assert_eq!(ProviderAttempts::from_headers(&headers), Some(attempts));
+    }
 }
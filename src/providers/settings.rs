@@ -0,0 +1,202 @@
+//! Runtime settings for providers defined in the config file.
+//!
+//! Settings are stored in the database and changed through
+//! `/admin/v1/providers/{name}/settings`. Every node keeps them in memory,
+//! applies them whenever a static provider is resolved, and reloads them
+//! periodically so changes made on another node take effect there too.
+
+use std::{collections::HashMap, sync::Arc};
+
+use super::{CircuitBreakerRegistry, FairQueueRegistry};
+use crate::{
+    compat::RwLock,
+    config::ProviderConfig,
+    db::{DbPool, DbResult},
+    models::ProviderSettings,
+};
+
+/// In-memory copy of the runtime settings of static providers.
+///
+/// Changing a provider's circuit breaker or fair queue settings drops the
+/// breaker or queue built from the old ones, so the next request builds a
+/// new one from the new settings. A breaker's open state is lost.
+#[derive(Clone)]
+pub struct ProviderSettingsRegistry {
+    settings: Arc<RwLock<HashMap<String, ProviderSettings>>>,
+    circuit_breakers: CircuitBreakerRegistry,
+    fair_queues: FairQueueRegistry,
+}
+
+impl ProviderSettingsRegistry {
+    /// Create an empty registry that resets the given breakers and queues
+    /// when a provider's settings change.
+    pub fn new(circuit_breakers: CircuitBreakerRegistry, fair_queues: FairQueueRegistry) -> Self {
+        Self {
+            settings: Arc::new(RwLock::new(HashMap::new())),
+            circuit_breakers,
+            fair_queues,
+        }
+    }
+
+    /// Get the settings of a provider, if it has any.
+    pub fn get(&self, provider_name: &str) -> Option<ProviderSettings> {
+        self.settings.read().get(provider_name).cloned()
+    }
+
+    /// Whether requests may be routed to a provider.
+    pub fn is_enabled(&self, provider_name: &str) -> bool {
+        self.settings
+            .read()
+            .get(provider_name)
+            .is_none_or(ProviderSettings::is_enabled)
+    }
+
+    /// The provider's file config with its settings applied, or `None` if
+    /// the provider is disabled.
+    pub fn apply(&self, provider_name: &str, config: &ProviderConfig) -> Option<ProviderConfig> {
+        let settings = self.settings.read();
+        let mut config = config.clone();
+        match settings.get(provider_name) {
+            Some(s) if !s.is_enabled() => return None,
+            Some(s) => s.apply_to(&mut config),
+            None => {}
+        }
+        Some(config)
+    }
+
+    /// Use `settings` for their provider from now on.
+    pub fn set(&self, settings: ProviderSettings) {
+        let name = settings.provider_name.clone();
+        let previous = self.settings.write().insert(name.clone(), settings.clone());
+        if settings.changes_limits(previous.as_ref()) {
+            self.reset_limits(&name);
+        }
+    }
+
+    /// Go back to the file config for a provider.
+    pub fn remove(&self, provider_name: &str) {
+        let previous = self.settings.write().remove(provider_name);
+        if previous.is_some_and(|p| p.changes_limits(None)) {
+            self.reset_limits(provider_name);
+        }
+    }
+
+    /// Replace all settings, e.g. with those just loaded from the database.
+    pub fn replace_all(&self, all: Vec<ProviderSettings>) {
+        let next: HashMap<String, ProviderSettings> = all
+            .into_iter()
+            .map(|s| (s.provider_name.clone(), s))
+            .collect();
+
+        let changed: Vec<String> = {
+            let mut settings = self.settings.write();
+            let names: std::collections::HashSet<&String> =
+                settings.keys().chain(next.keys()).collect();
+            let changed = names
+                .into_iter()
+                .filter(|name| match (next.get(*name), settings.get(*name)) {
+                    (Some(s), previous) => s.changes_limits(previous),
+                    (None, Some(previous)) => previous.changes_limits(None),
+                    (None, None) => false,
+                })
+                .cloned()
+                .collect();
+            *settings = next;
+            changed
+        };
+        for name in changed {
+            self.reset_limits(&name);
+        }
+    }
+
+    /// Reload all settings from the database.
+    pub async fn load(&self, db: &DbPool) -> DbResult<()> {
+        let all = db.provider_settings().list().await?;
+        self.replace_all(all);
+        Ok(())
+    }
+
+    fn reset_limits(&self, provider_name: &str) {
+        tracing::info!(
+            provider = %provider_name,
+            "Provider settings changed; rebuilding its circuit breaker and fair queue"
+        );
+        self.circuit_breakers.remove(provider_name);
+        self.fair_queues.remove(provider_name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::{
+        config::CircuitBreakerConfig,
+        models::{CircuitBreakerSettings, ProviderSettings},
+    };
+
+    fn settings(enabled: Option<bool>, failure_threshold: Option<u32>) -> ProviderSettings {
+        ProviderSettings {
+            id: Uuid::new_v4(),
+            provider_name: "test".into(),
+            enabled,
+            circuit_breaker: failure_threshold.map(|t| CircuitBreakerSettings {
+                failure_threshold: Some(t),
+                ..Default::default()
+            }),
+            retry: None,
+            max_concurrent: None,
+            updated_by: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn registry() -> (ProviderSettingsRegistry, CircuitBreakerRegistry) {
+        let breakers = CircuitBreakerRegistry::new();
+        let registry = ProviderSettingsRegistry::new(breakers.clone(), FairQueueRegistry::new());
+        (registry, breakers)
+    }
+
+    fn breaker_config() -> CircuitBreakerConfig {
+        CircuitBreakerConfig {
+            enabled: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_disabled_provider_does_not_resolve() {
+        let (registry, _) = registry();
+        let config: ProviderConfig = toml::from_str(r#"type = "test""#).unwrap();
+        assert!(registry.apply("test", &config).is_some());
+
+        registry.set(settings(Some(false), None));
+        assert!(!registry.is_enabled("test"));
+        assert!(registry.apply("test", &config).is_none());
+
+        registry.remove("test");
+        assert!(registry.is_enabled("test"));
+    }
+
+    #[test]
+    fn test_breaker_rebuilt_only_when_its_settings_change() {
+        let (registry, breakers) = registry();
+        breakers.get_or_create("test", &breaker_config());
+
+        // Disabling doesn't touch the breaker
+        registry.set(settings(Some(false), None));
+        assert!(breakers.get("test").is_some());
+
+        registry.set(settings(None, Some(2)));
+        assert!(breakers.get("test").is_none());
+
+        breakers.get_or_create("test", &breaker_config());
+        registry.replace_all(vec![settings(None, Some(2))]);
+        assert!(breakers.get("test").is_some());
+        registry.replace_all(vec![]);
+        assert!(breakers.get("test").is_none());
+    }
+}
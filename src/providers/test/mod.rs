@@ -1230,11 +1230,14 @@ mod tests {
             models: std::collections::HashMap::new(),
             retry: Default::default(),
             circuit_breaker: Default::default(),
+            fair_queue: None,
+            adaptive_concurrency: None,
             fallback_providers: vec![],
             model_fallbacks: std::collections::HashMap::new(),
             health_check: Default::default(),
             catalog_provider: None,
             sovereignty: None,
+            max_response_bytes: None,
         };
 
         let provider = TestProvider::from_config(&config);
@@ -1215,7 +1215,10 @@ pub(super) fn convert_vertex_to_responses_response(
     // Build usage, including thoughts_token_count as reasoning_tokens
     let usage = vertex.usage_metadata.map(|u| ResponsesUsage {
         input_tokens: u.prompt_token_count,
-        input_tokens_details: ResponsesUsageInputTokensDetails { cached_tokens: 0 },
+        input_tokens_details: ResponsesUsageInputTokensDetails {
+            cached_tokens: 0,
+            cache_creation_input_tokens: 0,
+        },
         output_tokens: u.candidates_token_count,
         output_tokens_details: ResponsesUsageOutputTokensDetails {
             reasoning_tokens: u.thoughts_token_count,
@@ -11,16 +11,24 @@ mod convert;
 mod stream;
 mod types;
 
-use std::{path::Path, sync::Arc, time::Duration};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    path::Path,
+    sync::{Arc, LazyLock, Mutex},
+    time::Duration,
+};
 
 use async_trait::async_trait;
 use axum::response::Response;
+use chrono::{DateTime, Utc};
 use convert::{
     convert_chat_completion_reasoning_to_thinking_config, convert_messages,
     convert_reasoning_to_thinking_config, convert_response, convert_responses_input_to_vertex,
     convert_responses_tool_choice_to_vertex, convert_responses_tools_to_vertex, convert_stop,
     convert_tool_choice, convert_tools, convert_vertex_to_responses_response,
 };
+use serde::Deserialize;
 #[cfg(test)]
 use stream::StreamState;
 pub use stream::{VertexToOpenAIStream, VertexToResponsesStream};
@@ -42,7 +50,9 @@ use crate::{
     },
     providers::{
         CircuitBreakerRegistry, ModelInfo, ModelsResponse, Provider, ProviderError,
+        anthropic::AnthropicProvider,
         circuit_breaker::CircuitBreaker,
+        endpoints::select_endpoint,
         error::VertexErrorParser,
         image::{ImageFetchConfig, preprocess_messages_for_images},
         response::{error_response, json_response, streaming_response},
@@ -52,6 +62,26 @@ use crate::{
 
 const VERTEX_AI_SCOPE: &str = "https://www.googleapis.com/auth/cloud-platform";
 
+/// Publisher whose models speak the Anthropic Messages API.
+const ANTHROPIC_PUBLISHER: &str = "anthropic";
+
+/// Impersonated tokens are renewed this long before they expire.
+const IMPERSONATION_REFRESH_BUFFER_SECS: i64 = 300;
+
+/// Impersonated access tokens and their expiry, keyed by base credentials
+/// and impersonation chain. Shared across requests, since providers are
+/// built per request.
+static IMPERSONATED_TOKENS: LazyLock<Mutex<HashMap<String, (String, DateTime<Utc>)>>> =
+    LazyLock::new(Default::default);
+
+/// IAM Credentials `generateAccessToken` response.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GenerateAccessTokenResponse {
+    access_token: String,
+    expire_time: DateTime<Utc>,
+}
+
 /// Authentication mode for the Vertex provider.
 #[derive(Clone)]
 enum AuthMode {
@@ -64,6 +94,8 @@ enum AuthMode {
         project: String,
         region: String,
         credentials: GcpCredentials,
+        /// Service accounts to impersonate; the last one makes requests.
+        impersonate: Vec<String>,
     },
 }
 
@@ -105,7 +137,22 @@ impl VertexProvider {
         registry: &CircuitBreakerRegistry,
         image_fetch_config: ImageFetchConfig,
     ) -> Self {
-        let circuit_breaker = registry.get_or_create(provider_name, &config.circuit_breaker);
+        let (config, circuit_breaker) = if config.endpoints.is_empty() {
+            let breaker = registry.get_or_create(provider_name, &config.circuit_breaker);
+            (Cow::Borrowed(config), breaker)
+        } else {
+            let (endpoint, breaker) = select_endpoint(
+                registry,
+                provider_name,
+                config.endpoint_selection,
+                &config.endpoints,
+                &config.circuit_breaker,
+            );
+            let config = endpoint.map_or(Cow::Borrowed(config), |e| {
+                Cow::Owned(config.with_endpoint(e))
+            });
+            (config, Some(breaker))
+        };
 
         let auth_mode = if let Some(api_key) = &config.api_key {
             AuthMode::ApiKey(api_key.clone())
@@ -114,6 +161,7 @@ impl VertexProvider {
                 project: config.project.clone().unwrap_or_default(),
                 region: config.region.clone().unwrap_or_default(),
                 credentials: config.credentials.clone(),
+                impersonate: config.impersonate_service_accounts.clone(),
             }
         };
 
@@ -131,27 +179,50 @@ impl VertexProvider {
         }
     }
 
-    /// Get the base URL for API requests (without the model path).
-    fn base_url(&self) -> String {
+    /// Split a model ID into its publisher and the publisher's model name.
+    ///
+    /// `<publisher>/<model>` names the publisher explicitly. Otherwise
+    /// `claude-*` models belong to Anthropic and `gemini-*` models to Google,
+    /// and anything else to the configured publisher.
+    fn publisher_and_model<'a>(&'a self, model: &'a str) -> (&'a str, &'a str) {
+        if let Some((publisher, model)) = model.split_once('/') {
+            (publisher, model)
+        } else if model.starts_with("claude-") {
+            (ANTHROPIC_PUBLISHER, model)
+        } else if model.starts_with("gemini-") {
+            ("google", model)
+        } else {
+            (self.publisher.as_str(), model)
+        }
+    }
+
+    /// Get the base URL for a publisher's models (without the model path).
+    fn base_url(&self, publisher: &str) -> String {
         if let Some(override_url) = &self.base_url_override {
-            return override_url.clone();
+            // Overrides name the configured publisher; swap in the routed one
+            return override_url.replace(
+                &format!("/publishers/{}/", self.publisher),
+                &format!("/publishers/{publisher}/"),
+            );
         }
 
         match &self.auth_mode {
             AuthMode::ApiKey(_) => {
                 // API key mode: global endpoint
-                format!(
-                    "https://aiplatform.googleapis.com/v1/publishers/{}/models",
-                    self.publisher
-                )
+                format!("https://aiplatform.googleapis.com/v1/publishers/{publisher}/models")
             }
             AuthMode::OAuth {
                 project, region, ..
             } => {
-                // OAuth mode: regional endpoint with project path
+                // OAuth mode: regional endpoint with project path. The
+                // `global` location has no regional host.
+                let host = if region == "global" {
+                    "aiplatform.googleapis.com".to_string()
+                } else {
+                    format!("{region}-aiplatform.googleapis.com")
+                };
                 format!(
-                    "https://{}-aiplatform.googleapis.com/v1/projects/{}/locations/{}/publishers/{}/models",
-                    region, project, region, self.publisher
+                    "https://{host}/v1/projects/{project}/locations/{region}/publishers/{publisher}/models"
                 )
             }
         }
@@ -162,7 +233,8 @@ impl VertexProvider {
     /// query string — query parameters end up in HTTP access logs and tracing
     /// span attributes.
     fn model_url(&self, model: &str, endpoint: &str, stream: bool) -> String {
-        let base = self.base_url();
+        let (publisher, model) = self.publisher_and_model(model);
+        let base = self.base_url(publisher);
         let mut url = format!("{}/{}:{}", base, model, endpoint);
         if stream {
             url.push_str("?alt=sse");
@@ -172,10 +244,14 @@ impl VertexProvider {
 
     /// Get an access token for OAuth mode, refreshing if necessary.
     /// Returns None for API key mode (no token needed).
-    async fn get_token(&self) -> Result<Option<String>, ProviderError> {
-        let credentials = match &self.auth_mode {
+    async fn get_token(&self, client: &reqwest::Client) -> Result<Option<String>, ProviderError> {
+        let (credentials, impersonate) = match &self.auth_mode {
             AuthMode::ApiKey(_) => return Ok(None),
-            AuthMode::OAuth { credentials, .. } => credentials,
+            AuthMode::OAuth {
+                credentials,
+                impersonate,
+                ..
+            } => (credentials, impersonate),
         };
 
         // Reuse the cached `TokenSourceProvider` if we already created one. The
@@ -215,7 +291,98 @@ impl VertexProvider {
             .map(str::to_owned)
             .unwrap_or(token);
 
-        Ok(Some(token))
+        if impersonate.is_empty() {
+            return Ok(Some(token));
+        }
+        self.impersonate(client, credentials, impersonate, &token)
+            .await
+            .map(Some)
+    }
+
+    /// Exchange a base access token for one belonging to the last service
+    /// account in `chain`, through the IAM Credentials API. Earlier accounts
+    /// are delegates.
+    async fn impersonate(
+        &self,
+        client: &reqwest::Client,
+        credentials: &GcpCredentials,
+        chain: &[String],
+        base_token: &str,
+    ) -> Result<String, ProviderError> {
+        let Some((target, delegates)) = chain.split_last() else {
+            return Ok(base_token.to_string());
+        };
+
+        let key = format!(
+            "{}|{}",
+            serde_json::to_string(credentials).unwrap_or_default(),
+            chain.join(",")
+        );
+        let renew_at = Utc::now() + chrono::Duration::seconds(IMPERSONATION_REFRESH_BUFFER_SECS);
+        if let Some((token, expires)) = IMPERSONATED_TOKENS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&key)
+            && *expires > renew_at
+        {
+            return Ok(token.clone());
+        }
+
+        let url = format!(
+            "https://iamcredentials.googleapis.com/v1/projects/-/serviceAccounts/{target}:generateAccessToken"
+        );
+        let delegates: Vec<String> = delegates
+            .iter()
+            .map(|account| format!("projects/-/serviceAccounts/{account}"))
+            .collect();
+        let response = client
+            .post(&url)
+            .bearer_auth(base_token)
+            .timeout(self.timeout)
+            .json(&serde_json::json!({
+                "delegates": delegates,
+                "scope": [VERTEX_AI_SCOPE],
+            }))
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(ProviderError::Internal(format!(
+                "Failed to impersonate service account '{target}': {status} - {body}"
+            )));
+        }
+
+        let generated: GenerateAccessTokenResponse = response.json().await?;
+        IMPERSONATED_TOKENS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(key, (generated.access_token.clone(), generated.expire_time));
+        Ok(generated.access_token)
+    }
+
+    /// An Anthropic Messages API client for Claude models on this provider's
+    /// project and region.
+    async fn anthropic(
+        &self,
+        client: &reqwest::Client,
+    ) -> Result<AnthropicProvider, ProviderError> {
+        let token = self.get_token(client).await?.ok_or_else(|| {
+            ProviderError::Unsupported(
+                "Claude models on Vertex AI need OAuth credentials, not an API key".to_string(),
+            )
+        })?;
+        Ok(AnthropicProvider::for_vertex(
+            self.base_url(ANTHROPIC_PUBLISHER),
+            token,
+            self.timeout,
+            self.retry.clone(),
+            self.circuit_breaker_config.clone(),
+            self.circuit_breaker.clone(),
+            self.streaming_buffer.clone(),
+            self.image_fetch_config.clone(),
+        ))
     }
 
     /// Build a `DefaultTokenSourceProvider` for the configured credentials.
@@ -321,6 +488,17 @@ impl Provider for VertexProvider {
             .clone()
             .unwrap_or_else(|| "gemini-1.5-pro".to_string());
 
+        // Claude models speak the Messages API rather than generateContent
+        if let (ANTHROPIC_PUBLISHER, claude_model) = self.publisher_and_model(&model) {
+            let mut payload = payload;
+            payload.model = Some(claude_model.to_string());
+            return self
+                .anthropic(client)
+                .await?
+                .create_chat_completion(client, payload)
+                .await;
+        }
+
         // Preprocess messages to convert HTTP image URLs to data URLs
         let mut messages = payload.messages;
         preprocess_messages_for_images(client, &mut messages, Some(&self.image_fetch_config)).await;
@@ -361,7 +539,7 @@ impl Provider for VertexProvider {
         // Pre-serialize request body before retry loop to avoid repeated serialization
         let body = serde_json::to_vec(&vertex_request).unwrap_or_default();
 
-        let token = self.get_token().await?;
+        let token = self.get_token(client).await?;
         let endpoint = if stream {
             "streamGenerateContent"
         } else {
@@ -437,6 +615,17 @@ impl Provider for VertexProvider {
             .clone()
             .unwrap_or_else(|| "gemini-2.0-flash".to_string());
 
+        // Claude models speak the Messages API rather than generateContent
+        if let (ANTHROPIC_PUBLISHER, claude_model) = self.publisher_and_model(&model) {
+            let mut payload = payload;
+            payload.model = Some(claude_model.to_string());
+            return self
+                .anthropic(client)
+                .await?
+                .create_responses(client, payload)
+                .await;
+        }
+
         let stream = payload.stream;
         let echo_fields = payload.echo_fields_json();
 
@@ -476,7 +665,7 @@ impl Provider for VertexProvider {
         // Pre-serialize request body before retry loop to avoid repeated serialization
         let body = serde_json::to_vec(&vertex_request).unwrap_or_default();
 
-        let token = self.get_token().await?;
+        let token = self.get_token(client).await?;
         let endpoint = if stream {
             "streamGenerateContent"
         } else {
@@ -615,7 +804,7 @@ impl Provider for VertexProvider {
         // Pre-serialize request body before retry loop to avoid repeated serialization
         let body = serde_json::to_vec(&vertex_request).unwrap_or_default();
 
-        let token = self.get_token().await?;
+        let token = self.get_token(client).await?;
         let url = self.model_url(&model, "predict", false);
 
         let response = with_circuit_breaker_and_retry(
@@ -973,3 +1162,103 @@ mod streaming_tests {
         assert!(content_str.contains(r#""content":"Hello world""#));
     }
 }
+
+#[cfg(test)]
+mod routing_tests {
+    use super::*;
+
+    fn vertex(toml: &str) -> VertexProvider {
+        let config: VertexProviderConfig = toml::from_str(toml).unwrap();
+        VertexProvider::from_config_with_registry(&config, "vertex", &CircuitBreakerRegistry::new())
+    }
+
+    #[test]
+    fn test_publisher_routing_by_model_id() {
+        let provider = vertex(
+            r#"
+            project = "my-project"
+            region = "us-east5"
+            publisher = "meta"
+        "#,
+        );
+
+        assert_eq!(
+            provider.publisher_and_model("claude-sonnet-4@20250514"),
+            ("anthropic", "claude-sonnet-4@20250514")
+        );
+        assert_eq!(
+            provider.publisher_and_model("gemini-2.5-pro"),
+            ("google", "gemini-2.5-pro")
+        );
+        assert_eq!(
+            provider.publisher_and_model("mistralai/mistral-large"),
+            ("mistralai", "mistral-large")
+        );
+        assert_eq!(
+            provider.publisher_and_model("llama-4-maverick"),
+            ("meta", "llama-4-maverick")
+        );
+        assert_eq!(
+            provider.model_url("claude-sonnet-4@20250514", "rawPredict", false),
+            "https://us-east5-aiplatform.googleapis.com/v1/projects/my-project/locations/us-east5/publishers/anthropic/models/claude-sonnet-4@20250514:rawPredict"
+        );
+    }
+
+    #[test]
+    fn test_global_region_and_override_urls() {
+        let provider = vertex(
+            r#"
+            project = "my-project"
+            region = "global"
+        "#,
+        );
+        assert_eq!(
+            provider.base_url("google"),
+            "https://aiplatform.googleapis.com/v1/projects/my-project/locations/global/publishers/google/models"
+        );
+
+        let provider = vertex(
+            r#"
+            project = "my-project"
+            region = "us-east5"
+            base_url = "https://vertex.internal/v1/projects/my-project/locations/us-east5/publishers/google/models"
+        "#,
+        );
+        assert_eq!(
+            provider.base_url("anthropic"),
+            "https://vertex.internal/v1/projects/my-project/locations/us-east5/publishers/anthropic/models"
+        );
+    }
+
+    #[test]
+    fn test_regional_endpoint_failover() {
+        let config: VertexProviderConfig = toml::from_str(
+            r#"
+            project = "my-project"
+            region = "us-east5"
+
+            [circuit_breaker]
+            enabled = true
+            failure_threshold = 1
+
+            [[endpoints]]
+            name = "europe"
+            region = "europe-west1"
+            priority = 1
+        "#,
+        )
+        .unwrap();
+        let registry = CircuitBreakerRegistry::new();
+
+        let provider = VertexProvider::from_config_with_registry(&config, "vertex", &registry);
+        assert!(provider.base_url("google").contains("us-east5"));
+
+        provider.circuit_breaker.as_ref().unwrap().record_failure();
+        let provider = VertexProvider::from_config_with_registry(&config, "vertex", &registry);
+        assert!(
+            provider
+                .base_url("google")
+                .starts_with("https://europe-west1-aiplatform.googleapis.com/")
+        );
+    }
+}
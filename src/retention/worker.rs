@@ -8,7 +8,16 @@ use std::sync::Arc;
 
 use chrono::{Duration, Utc};
 
-use crate::{config::RetentionConfig, db::DbPool, observability::metrics};
+use crate::{
+    config::RetentionConfig,
+    db::DbPool,
+    jobs::{
+        JobScheduler,
+        leader_lock::{self, LeadershipOutcome, keys},
+    },
+    models::JobTrigger,
+    observability::metrics,
+};
 
 /// Results from a single retention run.
 #[derive(Debug, Default)]
@@ -19,12 +28,17 @@ pub struct RetentionRunResult {
     pub audit_logs_deleted: u64,
     /// Number of conversations hard-deleted.
     pub conversations_deleted: u64,
+    /// Number of email send log entries deleted.
+    pub email_log_deleted: u64,
 }
 
 impl RetentionRunResult {
     /// Total number of records deleted across all tables.
     pub fn total(&self) -> u64 {
-        self.usage_records_deleted + self.audit_logs_deleted + self.conversations_deleted
+        self.usage_records_deleted
+            + self.audit_logs_deleted
+            + self.conversations_deleted
+            + self.email_log_deleted
     }
 
     /// Check if any records were deleted.
@@ -35,9 +49,14 @@ impl RetentionRunResult {
 
 /// Starts the retention worker as a background task.
 ///
-/// The worker runs in a loop, purging old data at the configured interval.
-/// It will run indefinitely until the task is cancelled.
-pub async fn start_retention_worker(db: Arc<DbPool>, config: RetentionConfig) {
+/// The worker runs in a loop, purging old data at the configured interval
+/// or when triggered through the jobs admin API. It will run indefinitely
+/// until the task is cancelled.
+pub async fn start_retention_worker(
+    db: Arc<DbPool>,
+    config: RetentionConfig,
+    scheduler: Arc<JobScheduler>,
+) {
     if !config.enabled {
         tracing::info!("Retention worker disabled by configuration");
         return;
@@ -59,36 +78,66 @@ pub async fn start_retention_worker(db: Arc<DbPool>, config: RetentionConfig) {
         usage_records_days = config.periods.usage_records_days,
         audit_logs_days = config.periods.audit_logs_days,
         conversations_deleted_days = config.periods.conversations_deleted_days,
+        email_log_days = config.periods.email_log_days,
         dry_run = config.safety.dry_run,
         "Starting retention worker{}",
         dry_run_msg
     );
 
-    let interval = config.interval();
+    let job = scheduler.register("retention", config.interval());
+    let mut trigger = JobTrigger::Scheduled;
 
     loop {
-        match run_retention(&db, &config).await {
-            Ok(result) => {
-                if result.has_deletions() {
-                    tracing::info!(
-                        usage_records = result.usage_records_deleted,
-                        audit_logs = result.audit_logs_deleted,
-                        conversations = result.conversations_deleted,
-                        total = result.total(),
-                        dry_run = config.safety.dry_run,
-                        "Retention run complete{}",
+        if job.should_run(trigger).await {
+            // One replica purges per tick; the others skip it.
+            let guard = match leader_lock::try_acquire(&db, keys::RETENTION).await {
+                LeadershipOutcome::Leader(g) => Some(g),
+                LeadershipOutcome::NotLeader => {
+                    tracing::trace!("retention: not leader this tick, skipping");
+                    trigger = job.tick().await;
+                    continue;
+                }
+                LeadershipOutcome::NoCoordination => None,
+            };
+
+            let result = job
+                .run(trigger, run_retention(&db, &config), |result| {
+                    Some(format!(
+                        "{} usage records, {} audit logs, {} conversations and {} email log \
+                         entries deleted{}",
+                        result.usage_records_deleted,
+                        result.audit_logs_deleted,
+                        result.conversations_deleted,
+                        result.email_log_deleted,
                         dry_run_msg
-                    );
-                } else {
-                    tracing::debug!("Retention run complete, no records to delete");
+                    ))
+                })
+                .await;
+            match result {
+                Ok(result) => {
+                    if result.has_deletions() {
+                        tracing::info!(
+                            usage_records = result.usage_records_deleted,
+                            audit_logs = result.audit_logs_deleted,
+                            conversations = result.conversations_deleted,
+                            email_log = result.email_log_deleted,
+                            total = result.total(),
+                            dry_run = config.safety.dry_run,
+                            "Retention run complete{}",
+                            dry_run_msg
+                        );
+                    } else {
+                        tracing::debug!("Retention run complete, no records to delete");
+                    }
+                }
+                Err(e) => {
+                    tracing::error!(error = %e, "Error running retention");
                 }
             }
-            Err(e) => {
-                tracing::error!(error = %e, "Error running retention");
-            }
+            drop(guard);
         }
 
-        tokio::time::sleep(interval).await;
+        trigger = job.tick().await;
     }
 }
 
@@ -117,6 +166,12 @@ async fn run_retention(
         result.conversations_deleted = deleted;
     }
 
+    // Delete email send log entries
+    if config.periods.should_retain_email_log() {
+        let deleted = delete_email_log(db, config).await?;
+        result.email_log_deleted = deleted;
+    }
+
     Ok(result)
 }
 
@@ -237,6 +292,45 @@ async fn delete_conversations(
     Ok(deleted)
 }
 
+/// Delete email send log entries older than the retention period.
+async fn delete_email_log(
+    db: &Arc<DbPool>,
+    config: &RetentionConfig,
+) -> Result<u64, Box<dyn std::error::Error + Send + Sync>> {
+    let cutoff = Utc::now() - Duration::days(config.periods.email_log_days as i64);
+
+    if config.safety.dry_run {
+        tracing::info!(
+            cutoff = %cutoff,
+            "DRY RUN: Would delete email log entries before {}",
+            cutoff
+        );
+        return Ok(0);
+    }
+
+    let max_deletes = if config.safety.max_deletes_per_run == 0 {
+        u64::MAX
+    } else {
+        config.safety.max_deletes_per_run
+    };
+
+    let deleted = db
+        .email_log()
+        .delete_before(cutoff, config.safety.batch_size, max_deletes)
+        .await?;
+
+    if deleted > 0 {
+        tracing::debug!(
+            deleted = deleted,
+            cutoff = %cutoff,
+            "Deleted email log entries"
+        );
+        metrics::record_retention_deletion("email_log", deleted);
+    }
+
+    Ok(deleted)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -247,8 +341,9 @@ mod tests {
             usage_records_deleted: 100,
             audit_logs_deleted: 25,
             conversations_deleted: 10,
+            email_log_deleted: 5,
         };
-        assert_eq!(result.total(), 135);
+        assert_eq!(result.total(), 140);
     }
 
     #[test]
@@ -269,6 +364,7 @@ mod tests {
         assert_eq!(result.usage_records_deleted, 0);
         assert_eq!(result.audit_logs_deleted, 0);
         assert_eq!(result.conversations_deleted, 0);
+        assert_eq!(result.email_log_deleted, 0);
         assert_eq!(result.total(), 0);
     }
 }
@@ -10,7 +10,7 @@ use super::csv_export::{
     CsvResponse, export_access_inventory_csv, export_org_access_report_csv,
     export_stale_access_csv, export_user_access_summary_csv,
 };
-use super::error::AdminError;
+use super::{error::AdminError, organizations::ListQuery};
 #[cfg(feature = "utoipa")]
 use crate::models::{
     AccessInventoryResponse, OrgAccessReportResponse, StaleAccessResponse,
@@ -44,6 +44,10 @@ fn get_services(state: &AppState) -> Result<&Services, AdminError> {
 /// - Last activity timestamp
 /// - Summary statistics
 ///
+/// Users are paginated with an opaque `cursor`; pass `pagination.next_cursor`
+/// from the previous page to continue. `offset` is still accepted for older
+/// clients but is ignored when a cursor is given.
+///
 /// Use `format=csv` for CSV export suitable for auditors and spreadsheets.
 #[cfg_attr(feature = "utoipa", utoipa::path(
     get,
@@ -66,13 +70,18 @@ pub async fn get_inventory(
 
     let services = get_services(&state)?;
 
-    let limit = query.limit.unwrap_or(100).min(1000);
-    let offset = query.offset.unwrap_or(0);
     let format = query.format;
+    let params = ListQuery {
+        limit: Some(query.limit.unwrap_or(100)),
+        cursor: query.cursor,
+        direction: query.direction,
+        include_deleted: None,
+    }
+    .try_into_with_cursor()?;
 
     let inventory = services
         .access_reviews
-        .get_access_inventory(query.org_id, limit, offset)
+        .get_access_inventory(query.org_id, params, query.offset)
         .await?;
 
     match format {
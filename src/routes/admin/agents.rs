@@ -0,0 +1,299 @@
+//! Admin API endpoints for an organization's agents.
+//!
+//! An agent is a model with a system prompt and the server-executed tools it
+//! may call. The organization's callers run agents with
+//! `POST /v1/agents/{agent_id}/runs`.
+
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use axum_valid::Valid;
+use serde::Serialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use super::{AuditActor, error::AdminError};
+use crate::{
+    AppState,
+    db::DbPool,
+    middleware::{AdminAuth, AuthzContext, ClientInfo},
+    models::{Agent, CreateAgent, CreateAuditLog, Organization, UpdateAgent},
+    services::{Services, agents},
+};
+
+fn get_services(state: &AppState) -> Result<&Services, AdminError> {
+    state.services.as_ref().ok_or(AdminError::ServicesRequired)
+}
+
+fn get_db(state: &AppState) -> Result<&DbPool, AdminError> {
+    state.db.as_deref().ok_or(AdminError::DatabaseRequired)
+}
+
+async fn get_org(state: &AppState, org_slug: &str) -> Result<Organization, AdminError> {
+    get_services(state)?
+        .organizations
+        .get_by_slug(org_slug)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("Organization '{}' not found", org_slug)))
+}
+
+/// Load an agent of `org`. Other organizations' agents are reported as
+/// missing.
+async fn get_agent(db: &DbPool, org: &Organization, agent_id: Uuid) -> Result<Agent, AdminError> {
+    db.agents()
+        .get_agent(agent_id)
+        .await?
+        .filter(|agent| agent.org_id == org.id)
+        .ok_or_else(|| AdminError::NotFound(format!("Agent '{agent_id}' not found")))
+}
+
+async fn validate_tools(
+    state: &AppState,
+    org: &Organization,
+    tools: &[crate::api_types::responses::ResponsesToolDefinition],
+) -> Result<(), AdminError> {
+    let org_tools: Vec<String> = get_services(state)?
+        .http_tools
+        .list_by_org(org.id)
+        .await?
+        .into_iter()
+        .map(|t| t.name)
+        .collect();
+    agents::validate_tools(&state.config.features, &org_tools, tools)
+        .map_err(AdminError::BadRequest)
+}
+
+async fn audit(
+    state: &AppState,
+    admin_auth: &AdminAuth,
+    client_info: ClientInfo,
+    action: &str,
+    agent: &Agent,
+) {
+    let Some(services) = &state.services else {
+        return;
+    };
+    let actor = AuditActor::from(admin_auth);
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: format!("agent.{action}"),
+            resource_type: "agent".to_string(),
+            resource_id: agent.id,
+            org_id: Some(agent.org_id),
+            project_id: None,
+            details: json!({ "name": agent.name, "model": agent.model }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+}
+
+/// List of an organization's agents, ordered by name
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct AgentListResponse {
+    pub data: Vec<Agent>,
+}
+
+/// List an organization's agents
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/organizations/{org_slug}/agents",
+    tag = "agents",
+    operation_id = "agent_list",
+    params(("org_slug" = String, Path, description = "Organization slug")),
+    responses(
+        (status = 200, description = "The organization's agents", body = AgentListResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn list(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(org_slug): Path<String>,
+) -> Result<Json<AgentListResponse>, AdminError> {
+    let org = get_org(&state, &org_slug).await?;
+    authz.require("agent", "list", None, Some(&org.id.to_string()), None, None)?;
+    let db = get_db(&state)?;
+    let data = db.agents().list_agents(org.id).await?;
+    Ok(Json(AgentListResponse { data }))
+}
+
+/// Create an agent
+///
+/// Tools must be ones the gateway executes itself: `file_search`,
+/// `web_search`, `shell`, or function tools named after a configured HTTP
+/// tool, each enabled on the gateway.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/organizations/{org_slug}/agents",
+    tag = "agents",
+    operation_id = "agent_create",
+    params(("org_slug" = String, Path, description = "Organization slug")),
+    request_body = CreateAgent,
+    responses(
+        (status = 201, description = "Agent created", body = Agent),
+        (status = 400, description = "Invalid agent or unsupported tool", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found", body = crate::openapi::ErrorResponse),
+        (status = 409, description = "Agent with same name already exists", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn create(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path(org_slug): Path<String>,
+    Valid(Json(input)): Valid<Json<CreateAgent>>,
+) -> Result<(StatusCode, Json<Agent>), AdminError> {
+    let org = get_org(&state, &org_slug).await?;
+    authz.require(
+        "agent",
+        "create",
+        None,
+        Some(&org.id.to_string()),
+        None,
+        None,
+    )?;
+    let db = get_db(&state)?;
+    validate_tools(&state, &org, &input.tools).await?;
+
+    let agent = db.agents().create_agent(org.id, input).await?;
+
+    audit(&state, &admin_auth, client_info, "create", &agent).await;
+    Ok((StatusCode::CREATED, Json(agent)))
+}
+
+/// Get an agent
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/organizations/{org_slug}/agents/{agent_id}",
+    tag = "agents",
+    operation_id = "agent_get",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("agent_id" = Uuid, Path, description = "Agent ID"),
+    ),
+    responses(
+        (status = 200, description = "The agent", body = Agent),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Agent not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn get(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path((org_slug, agent_id)): Path<(String, Uuid)>,
+) -> Result<Json<Agent>, AdminError> {
+    let org = get_org(&state, &org_slug).await?;
+    authz.require(
+        "agent",
+        "read",
+        Some(&agent_id.to_string()),
+        Some(&org.id.to_string()),
+        None,
+        None,
+    )?;
+    let db = get_db(&state)?;
+    Ok(Json(get_agent(db, &org, agent_id).await?))
+}
+
+/// Update an agent
+///
+/// Omitted fields are left unchanged. Runs in progress keep the definition
+/// they started with.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    patch,
+    path = "/admin/v1/organizations/{org_slug}/agents/{agent_id}",
+    tag = "agents",
+    operation_id = "agent_update",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("agent_id" = Uuid, Path, description = "Agent ID"),
+    ),
+    request_body = UpdateAgent,
+    responses(
+        (status = 200, description = "Agent updated", body = Agent),
+        (status = 400, description = "Invalid agent or unsupported tool", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Agent not found", body = crate::openapi::ErrorResponse),
+        (status = 409, description = "Agent with same name already exists", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn update(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path((org_slug, agent_id)): Path<(String, Uuid)>,
+    Valid(Json(input)): Valid<Json<UpdateAgent>>,
+) -> Result<Json<Agent>, AdminError> {
+    let org = get_org(&state, &org_slug).await?;
+    authz.require(
+        "agent",
+        "update",
+        Some(&agent_id.to_string()),
+        Some(&org.id.to_string()),
+        None,
+        None,
+    )?;
+    let db = get_db(&state)?;
+    get_agent(db, &org, agent_id).await?;
+    if let Some(tools) = &input.tools {
+        validate_tools(&state, &org, tools).await?;
+    }
+
+    let agent = db.agents().update_agent(agent_id, input).await?;
+
+    audit(&state, &admin_auth, client_info, "update", &agent).await;
+    Ok(Json(agent))
+}
+
+/// Delete an agent
+///
+/// The agent's runs and their steps are deleted with it.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    delete,
+    path = "/admin/v1/organizations/{org_slug}/agents/{agent_id}",
+    tag = "agents",
+    operation_id = "agent_delete",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("agent_id" = Uuid, Path, description = "Agent ID"),
+    ),
+    responses(
+        (status = 204, description = "Agent deleted"),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Agent not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn delete(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path((org_slug, agent_id)): Path<(String, Uuid)>,
+) -> Result<StatusCode, AdminError> {
+    let org = get_org(&state, &org_slug).await?;
+    authz.require(
+        "agent",
+        "delete",
+        Some(&agent_id.to_string()),
+        Some(&org.id.to_string()),
+        None,
+        None,
+    )?;
+    let db = get_db(&state)?;
+    let agent = get_agent(db, &org, agent_id).await?;
+    db.agents().delete_agent(agent_id).await?;
+
+    audit(&state, &admin_auth, client_info, "delete", &agent).await;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -8,14 +8,19 @@ use serde::{Deserialize, Serialize};
 use serde_json::json;
 use uuid::Uuid;
 
-use super::{AuditActor, error::AdminError, organizations::ListQuery};
+use super::{
+    AuditActor,
+    error::AdminError,
+    organizations::{LabelQuery, ListQuery},
+};
 use crate::{
     AppState,
     cache::CacheKeys,
     middleware::{AdminAuth, AuthzContext, ClientInfo},
     models::{
-        ApiKey, ApiKeyScope, CreateApiKey, CreateAuditLog, CreatedApiKey, validate_ip_allowlist,
-        validate_model_patterns, validate_scopes,
+        ADMIN_SCOPE_AREAS, ApiKey, ApiKeyHasher, ApiKeyScope, CreateApiKey, CreateAuditLog,
+        CreatedApiKey, SetLabels, validate_ip_allowlist, validate_labels, validate_model_patterns,
+        validate_scopes,
     },
     openapi::PaginationMeta,
     services::Services,
@@ -49,9 +54,11 @@ pub(super) fn validate_api_key_input(
         && let Err(invalid_scopes) = validate_scopes(scopes)
     {
         return Err(AdminError::Validation(format!(
-            "Invalid scopes: {}. Valid scopes: {}",
+            "Invalid scopes: {}. Valid scopes: {}, optionally suffixed with :read or :write; \
+             admin also accepts admin:<area>[:read|:write] with area one of: {}",
             invalid_scopes.join(", "),
-            ApiKeyScope::all_names().join(", ")
+            ApiKeyScope::all_names().join(", "),
+            ADMIN_SCOPE_AREAS.join(", ")
         )));
     }
 
@@ -281,12 +288,19 @@ pub(super) async fn check_owner_modify_authz(
     Ok(())
 }
 
-/// Enforce the per-scope `max_api_keys_per_*` limits before creating a key.
+/// Enforce the per-scope `max_api_keys_per_*` limits and the owning
+/// organization's plan before creating a key.
 pub(crate) async fn check_owner_create_limits(
     services: &crate::services::Services,
     owner: &crate::models::ApiKeyOwner,
     limits: &crate::config::ResourceLimits,
+    entitlements: &crate::config::EntitlementsConfig,
 ) -> Result<(), AdminError> {
+    services
+        .entitlements
+        .check_api_key_limit(entitlements, owner)
+        .await?;
+
     match owner {
         crate::models::ApiKeyOwner::Organization { org_id } => {
             let max = limits.max_api_keys_per_org;
@@ -582,10 +596,18 @@ pub async fn create(
     )?;
 
     check_owner_create_authz(services, &authz, &input.owner).await?;
-    check_owner_create_limits(services, &input.owner, &state.config.limits.resource_limits).await?;
+    check_owner_create_limits(
+        services,
+        &input.owner,
+        &state.config.limits.resource_limits,
+        &state.config.features.entitlements,
+    )
+    .await?;
 
     // Get the key generation prefix from config
-    let prefix = state.config.auth.api_key_config().generation_prefix();
+    let api_key_config = state.config.auth.api_key_config();
+    let prefix = api_key_config.generation_prefix();
+    let hasher = ApiKeyHasher::from_config(api_key_config);
 
     // Capture owner info for audit log before consuming input
     let (org_id, project_id) = match &input.owner {
@@ -597,7 +619,7 @@ pub async fn create(
     };
 
     // Create API key through service
-    let created = services.api_keys.create(input, &prefix).await?;
+    let created = services.api_keys.create(input, &prefix, &hasher).await?;
 
     // Log audit event (fire-and-forget, don't fail the request if logging fails)
     let _ = services
@@ -631,10 +653,11 @@ pub async fn create(
     params(
         ("org_slug" = String, Path, description = "Organization slug"),
         ListQuery,
+        LabelQuery,
     ),
     responses(
         (status = 200, description = "List of API keys", body = ApiKeyListResponse),
-        (status = 400, description = "Invalid cursor or direction", body = crate::openapi::ErrorResponse),
+        (status = 400, description = "Invalid cursor, direction or label selector", body = crate::openapi::ErrorResponse),
         (status = 404, description = "Organization not found", body = crate::openapi::ErrorResponse),
     )
 ))]
@@ -643,6 +666,7 @@ pub async fn list_by_org(
     Extension(authz): Extension<AuthzContext>,
     Path(org_slug): Path<String>,
     Query(query): Query<ListQuery>,
+    Query(labels): Query<LabelQuery>,
 ) -> Result<Json<ApiKeyListResponse>, AdminError> {
     let services = get_services(&state)?;
 
@@ -663,7 +687,7 @@ pub async fn list_by_org(
     )?;
 
     let limit = query.limit.unwrap_or(100);
-    let params = query.try_into_with_cursor()?;
+    let params = labels.apply(query.try_into_with_cursor()?)?;
 
     let result = services.api_keys.list_by_org(org.id, params).await?;
 
@@ -690,10 +714,11 @@ pub async fn list_by_org(
         ("org_slug" = String, Path, description = "Organization slug"),
         ("project_slug" = String, Path, description = "Project slug"),
         ListQuery,
+        LabelQuery,
     ),
     responses(
         (status = 200, description = "List of API keys", body = ApiKeyListResponse),
-        (status = 400, description = "Invalid cursor or direction", body = crate::openapi::ErrorResponse),
+        (status = 400, description = "Invalid cursor, direction or label selector", body = crate::openapi::ErrorResponse),
         (status = 404, description = "Organization or project not found", body = crate::openapi::ErrorResponse),
     )
 ))]
@@ -702,6 +727,7 @@ pub async fn list_by_project(
     Extension(authz): Extension<AuthzContext>,
     Path((org_slug, project_slug)): Path<(String, String)>,
     Query(query): Query<ListQuery>,
+    Query(labels): Query<LabelQuery>,
 ) -> Result<Json<ApiKeyListResponse>, AdminError> {
     let services = get_services(&state)?;
 
@@ -734,7 +760,7 @@ pub async fn list_by_project(
     )?;
 
     let limit = query.limit.unwrap_or(100);
-    let params = query.try_into_with_cursor()?;
+    let params = labels.apply(query.try_into_with_cursor()?)?;
 
     let result = services
         .api_keys
@@ -763,10 +789,11 @@ pub async fn list_by_project(
     params(
         ("user_id" = Uuid, Path, description = "User ID"),
         ListQuery,
+        LabelQuery,
     ),
     responses(
         (status = 200, description = "List of API keys", body = ApiKeyListResponse),
-        (status = 400, description = "Invalid cursor or direction", body = crate::openapi::ErrorResponse),
+        (status = 400, description = "Invalid cursor, direction or label selector", body = crate::openapi::ErrorResponse),
     )
 ))]
 pub async fn list_by_user(
@@ -774,6 +801,7 @@ pub async fn list_by_user(
     Extension(authz): Extension<AuthzContext>,
     Path(user_id): Path<Uuid>,
     Query(query): Query<ListQuery>,
+    Query(labels): Query<LabelQuery>,
 ) -> Result<Json<ApiKeyListResponse>, AdminError> {
     // Pass the target user_id through `resource_id` so policies can compare
     // it against the calling subject and reject cross-user listing.
@@ -782,7 +810,7 @@ pub async fn list_by_user(
     let services = get_services(&state)?;
 
     let limit = query.limit.unwrap_or(100);
-    let params = query.try_into_with_cursor()?;
+    let params = labels.apply(query.try_into_with_cursor()?)?;
 
     let result = services.api_keys.list_by_user(user_id, params).await?;
 
@@ -809,10 +837,11 @@ pub async fn list_by_user(
         ("org_slug" = String, Path, description = "Organization slug"),
         ("sa_slug" = String, Path, description = "Service account slug"),
         ListQuery,
+        LabelQuery,
     ),
     responses(
         (status = 200, description = "List of API keys", body = ApiKeyListResponse),
-        (status = 400, description = "Invalid cursor or direction", body = crate::openapi::ErrorResponse),
+        (status = 400, description = "Invalid cursor, direction or label selector", body = crate::openapi::ErrorResponse),
         (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
         (status = 404, description = "Organization or service account not found", body = crate::openapi::ErrorResponse),
     )
@@ -822,6 +851,7 @@ pub async fn list_by_service_account(
     Extension(authz): Extension<crate::middleware::AuthzContext>,
     Path((org_slug, sa_slug)): Path<(String, String)>,
     Query(query): Query<ListQuery>,
+    Query(labels): Query<LabelQuery>,
 ) -> Result<Json<ApiKeyListResponse>, AdminError> {
     let services = get_services(&state)?;
 
@@ -855,7 +885,7 @@ pub async fn list_by_service_account(
     )?;
 
     let limit = query.limit.unwrap_or(100);
-    let params = query.try_into_with_cursor()?;
+    let params = labels.apply(query.try_into_with_cursor()?)?;
 
     let result = services
         .api_keys
@@ -972,6 +1002,88 @@ pub async fn revoke(
     Ok(Json(()))
 }
 
+/// Set an API key's labels
+///
+/// Replaces all of the key's labels. Send an empty object to remove them.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    put,
+    path = "/admin/v1/api-keys/{key_id}/labels",
+    tag = "api-keys",
+    operation_id = "api_key_set_labels",
+    params(("key_id" = Uuid, Path, description = "API key ID")),
+    request_body = SetLabels,
+    responses(
+        (status = 200, description = "Labels saved", body = ApiKey),
+        (status = 400, description = "Invalid labels", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "API key not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn set_labels(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path(key_id): Path<Uuid>,
+    Json(input): Json<SetLabels>,
+) -> Result<Json<ApiKey>, AdminError> {
+    let services = get_services(&state)?;
+    let actor = AuditActor::from(&admin_auth);
+
+    // Fetch first so authz can scope by owner; see `revoke` for rationale.
+    let key = match services.api_keys.get_by_id(key_id).await? {
+        Some(k) => k,
+        None => {
+            authz.require(
+                "api_key",
+                "update",
+                Some(&key_id.to_string()),
+                None,
+                None,
+                None,
+            )?;
+            return Err(AdminError::NotFound(format!(
+                "API key '{}' not found",
+                key_id
+            )));
+        }
+    };
+    check_owner_modify_authz(services, &authz, "update", key_id, &key.owner).await?;
+
+    validate_labels(&input.labels).map_err(AdminError::Validation)?;
+
+    let updated = services.api_keys.set_labels(key_id, &input.labels).await?;
+
+    let (org_id, project_id) = match &key.owner {
+        crate::models::ApiKeyOwner::Organization { org_id } => (Some(*org_id), None),
+        crate::models::ApiKeyOwner::Project { project_id } => (None, Some(*project_id)),
+        _ => (None, None),
+    };
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "api_key.labels_update".to_string(),
+            resource_type: "api_key".to_string(),
+            resource_id: key_id,
+            org_id,
+            project_id,
+            details: json!({
+                "name": key.name,
+                "key_prefix": key.key_prefix,
+                "previous": key.labels,
+                "labels": input.labels,
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(Json(updated))
+}
+
 /// Default grace period for key rotation: 24 hours
 pub(super) const DEFAULT_GRACE_PERIOD_SECONDS: u64 = 86400;
 /// Maximum grace period: 7 days
@@ -1113,14 +1225,16 @@ pub async fn rotate(
     }
 
     // Get the key generation prefix from config
-    let prefix = state.config.auth.api_key_config().generation_prefix();
+    let api_key_config = state.config.auth.api_key_config();
+    let prefix = api_key_config.generation_prefix();
+    let hasher = ApiKeyHasher::from_config(api_key_config);
 
     let old_key = Some(old_key_for_authz);
 
     // Perform the rotation
     let created = services
         .api_keys
-        .rotate(key_id, grace_period_seconds, &prefix)
+        .rotate(key_id, grace_period_seconds, &prefix, &hasher)
         .await?;
 
     // Log audit event
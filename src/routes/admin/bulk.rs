@@ -0,0 +1,528 @@
+//! Bulk admin operations.
+//!
+//! Each endpoint applies one operation to many items and reports the outcome
+//! of every item, so a single bad entry doesn't fail the whole batch. Checks
+//! that apply to the batch as a whole (authorization on the target
+//! organization or key owner, batch size) still reject the request up front.
+
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+};
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+use validator::Validate;
+
+use super::{
+    AuditActor,
+    api_keys::{check_owner_modify_authz, invalidate_api_key_cache},
+    error::AdminError,
+};
+use crate::{
+    AppState,
+    middleware::{AdminAuth, AuthzContext, ClientInfo},
+    models::{ApiKey, ApiKeyOwner, CreateAuditLog, CreateUser, MembershipSource, Organization},
+    services::Services,
+};
+
+/// Maximum number of items accepted by a single bulk request.
+pub const MAX_BULK_ITEMS: usize = 1000;
+
+fn get_services(state: &AppState) -> Result<&Services, AdminError> {
+    state.services.as_ref().ok_or(AdminError::ServicesRequired)
+}
+
+fn check_batch_size(len: usize) -> Result<(), AdminError> {
+    if len > MAX_BULK_ITEMS {
+        return Err(AdminError::BadRequest(format!(
+            "Bulk requests accept at most {MAX_BULK_ITEMS} items, got {len}"
+        )));
+    }
+    Ok(())
+}
+
+fn default_member_role() -> String {
+    "member".to_string()
+}
+
+/// Outcome of a single item in a bulk request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum BulkItemStatus {
+    /// The operation was applied
+    Succeeded,
+    /// The operation failed; see `error`
+    Failed,
+    /// The item matched but was not changed (dry run)
+    Skipped,
+}
+
+/// Error reported for a failed item.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct BulkItemError {
+    /// Machine-readable error code, as in single-item error responses
+    pub code: String,
+    /// Human-readable error message
+    pub message: String,
+}
+
+impl From<AdminError> for BulkItemError {
+    fn from(err: AdminError) -> Self {
+        let (code, message) = match err {
+            AdminError::NotFound(msg) => ("not_found", msg),
+            AdminError::Conflict(msg) => ("conflict", msg),
+            AdminError::Validation(msg) => ("validation_error", msg),
+            AdminError::BadRequest(msg) => ("bad_request", msg),
+            AdminError::Forbidden(msg) => ("forbidden", msg),
+            err => {
+                tracing::error!(error = ?err, "Bulk item failed");
+                ("internal_error", "An internal error occurred".to_string())
+            }
+        };
+        Self {
+            code: code.to_string(),
+            message,
+        }
+    }
+}
+
+/// Result for one item in a bulk request.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct BulkItemResult {
+    /// Position of the item in the request (0-based). For filter-based
+    /// operations, the position in the matched set.
+    pub index: usize,
+    /// ID of the affected resource, when known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Uuid>,
+    /// Outcome of the item
+    pub status: BulkItemStatus,
+    /// Why the item failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<BulkItemError>,
+}
+
+/// Per-item results of a bulk request.
+#[derive(Debug, Clone, Default, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct BulkResponse {
+    /// Number of items applied
+    pub succeeded: usize,
+    /// Number of items that failed
+    pub failed: usize,
+    /// Number of items skipped (dry run)
+    pub skipped: usize,
+    /// One entry per item, in request order
+    pub results: Vec<BulkItemResult>,
+}
+
+impl BulkResponse {
+    fn push(&mut self, id: Option<Uuid>, outcome: Result<BulkItemStatus, AdminError>) {
+        let index = self.results.len();
+        let (status, error) = match outcome {
+            Ok(status) => (status, None),
+            Err(err) => (BulkItemStatus::Failed, Some(err.into())),
+        };
+        match status {
+            BulkItemStatus::Succeeded => self.succeeded += 1,
+            BulkItemStatus::Failed => self.failed += 1,
+            BulkItemStatus::Skipped => self.skipped += 1,
+        }
+        self.results.push(BulkItemResult {
+            index,
+            id,
+            status,
+            error,
+        });
+    }
+}
+
+/// Add a user to an organization, enforcing the per-org member limit.
+async fn add_org_member(
+    state: &AppState,
+    services: &Services,
+    actor: &AuditActor,
+    client_info: &ClientInfo,
+    org: &Organization,
+    user_id: Uuid,
+    role: &str,
+) -> Result<(), AdminError> {
+    let max = state.config.limits.resource_limits.max_members_per_org;
+    if max > 0 {
+        let count = services.users.count_org_members(org.id, false).await?;
+        if count >= max as i64 {
+            return Err(AdminError::Conflict(format!(
+                "Organization has reached the maximum number of members ({max})"
+            )));
+        }
+    }
+
+    services
+        .users
+        .add_to_org(user_id, org.id, role, MembershipSource::Manual)
+        .await?;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "membership.add_org".to_string(),
+            resource_type: "organization".to_string(),
+            resource_id: org.id,
+            org_id: Some(org.id),
+            project_id: None,
+            details: json!({
+                "user_id": user_id,
+                "org_slug": org.slug,
+                "org_name": org.name,
+                "role": role,
+                "bulk": true,
+            }),
+            ip_address: client_info.ip_address.clone(),
+            user_agent: client_info.user_agent.clone(),
+        })
+        .await;
+
+    Ok(())
+}
+
+/// Look up an organization and require permission to manage its members.
+async fn authorize_org_members(
+    services: &Services,
+    authz: &AuthzContext,
+    org_slug: &str,
+) -> Result<Organization, AdminError> {
+    let org = services
+        .organizations
+        .get_by_slug(org_slug)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("Organization '{}' not found", org_slug)))?;
+
+    authz.require(
+        "organization",
+        "update",
+        Some(&org.id.to_string()),
+        Some(&org.id.to_string()),
+        None,
+        None,
+    )?;
+    Ok(org)
+}
+
+/// Request to create many users
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct BulkCreateUsersRequest {
+    /// Users to create
+    pub users: Vec<CreateUser>,
+    /// Organization to add each created user to
+    pub org_slug: Option<String>,
+    /// Role for the organization membership (defaults to 'member')
+    #[serde(default = "default_member_role")]
+    pub role: String,
+}
+
+/// Create users in bulk
+///
+/// Creates each user and, when `org_slug` is set, adds it to that organization
+/// so users can be invited ahead of their first SSO login. Items fail
+/// independently; a user that was created but could not be added to the
+/// organization is reported as failed with its `id` set.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/users/bulk",
+    tag = "users",
+    operation_id = "user_bulk_create",
+    request_body = BulkCreateUsersRequest,
+    responses(
+        (status = 200, description = "Per-user results", body = BulkResponse),
+        (status = 400, description = "Too many items", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.bulk.create_users", skip_all)]
+pub async fn create_users(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Json(req): Json<BulkCreateUsersRequest>,
+) -> Result<Json<BulkResponse>, AdminError> {
+    authz.require("user", "create", None, None, None, None)?;
+    check_batch_size(req.users.len())?;
+
+    let services = get_services(&state)?;
+    let actor = AuditActor::from(&admin_auth);
+    let org = match &req.org_slug {
+        Some(slug) => Some(authorize_org_members(services, &authz, slug).await?),
+        None => None,
+    };
+
+    let mut response = BulkResponse::default();
+    for input in req.users {
+        if let Err(e) = input.validate() {
+            response.push(None, Err(AdminError::Validation(e.to_string())));
+            continue;
+        }
+
+        let user = match services.users.create(input).await {
+            Ok(user) => user,
+            Err(e) => {
+                response.push(None, Err(e.into()));
+                continue;
+            }
+        };
+
+        let _ = services
+            .audit_logs
+            .create(CreateAuditLog {
+                actor_type: actor.actor_type,
+                actor_id: actor.actor_id,
+                action: "user.create".to_string(),
+                resource_type: "user".to_string(),
+                resource_id: user.id,
+                org_id: None,
+                project_id: None,
+                details: json!({
+                    "email": user.email,
+                    "name": user.name,
+                    "external_id": user.external_id,
+                    "bulk": true,
+                }),
+                ip_address: client_info.ip_address.clone(),
+                user_agent: client_info.user_agent.clone(),
+            })
+            .await;
+
+        let outcome = match &org {
+            Some(org) => {
+                add_org_member(
+                    &state,
+                    services,
+                    &actor,
+                    &client_info,
+                    org,
+                    user.id,
+                    &req.role,
+                )
+                .await
+            }
+            None => Ok(()),
+        };
+        response.push(Some(user.id), outcome.map(|_| BulkItemStatus::Succeeded));
+    }
+
+    tracing::info!(
+        succeeded = response.succeeded,
+        failed = response.failed,
+        "Bulk user create via admin API"
+    );
+
+    Ok(Json(response))
+}
+
+/// Request to add many members to an organization
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct BulkAddMembersRequest {
+    /// Members to add
+    pub members: Vec<super::users::AddMemberRequest>,
+}
+
+/// Add members to an organization in bulk
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/organizations/{org_slug}/members/bulk",
+    tag = "users",
+    operation_id = "org_member_bulk_add",
+    params(("org_slug" = String, Path, description = "Organization slug")),
+    request_body = BulkAddMembersRequest,
+    responses(
+        (status = 200, description = "Per-member results", body = BulkResponse),
+        (status = 400, description = "Too many items", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.bulk.add_org_members", skip_all, fields(%org_slug))]
+pub async fn add_org_members(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path(org_slug): Path<String>,
+    Json(req): Json<BulkAddMembersRequest>,
+) -> Result<Json<BulkResponse>, AdminError> {
+    let services = get_services(&state)?;
+    let org = authorize_org_members(services, &authz, &org_slug).await?;
+    check_batch_size(req.members.len())?;
+    let actor = AuditActor::from(&admin_auth);
+
+    let mut response = BulkResponse::default();
+    for member in req.members {
+        let outcome = add_org_member(
+            &state,
+            services,
+            &actor,
+            &client_info,
+            &org,
+            member.user_id,
+            &member.role,
+        )
+        .await;
+        response.push(
+            Some(member.user_id),
+            outcome.map(|_| BulkItemStatus::Succeeded),
+        );
+    }
+
+    tracing::info!(
+        org_id = %org.id,
+        succeeded = response.succeeded,
+        failed = response.failed,
+        "Bulk org member add via admin API"
+    );
+
+    Ok(Json(response))
+}
+
+/// Request to revoke API keys matching a filter
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct BulkRevokeApiKeysRequest {
+    /// Owner whose keys are considered
+    pub owner: ApiKeyOwner,
+    /// Only revoke keys created more than this many days ago
+    pub older_than_days: Option<u32>,
+    /// Only revoke keys not used in this many days. Keys that were never used
+    /// count from their creation time.
+    pub unused_for_days: Option<u32>,
+    /// Report the matching keys without revoking them
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+impl BulkRevokeApiKeysRequest {
+    fn matches(&self, key: &ApiKey, now: chrono::DateTime<Utc>) -> bool {
+        if key.revoked_at.is_some() {
+            return false;
+        }
+        if let Some(days) = self.older_than_days
+            && key.created_at > now - Duration::days(days.into())
+        {
+            return false;
+        }
+        if let Some(days) = self.unused_for_days
+            && key.last_used_at.unwrap_or(key.created_at) > now - Duration::days(days.into())
+        {
+            return false;
+        }
+        true
+    }
+}
+
+/// Revoke API keys in bulk
+///
+/// Revokes every active key of `owner` that matches all the given filters.
+/// With no filters, all of the owner's active keys are revoked. Use
+/// `dry_run` to preview the matches, which are reported as `skipped`.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/api-keys/bulk-revoke",
+    tag = "api-keys",
+    operation_id = "api_key_bulk_revoke",
+    request_body = BulkRevokeApiKeysRequest,
+    responses(
+        (status = 200, description = "Per-key results", body = BulkResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Owner not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.bulk.revoke_api_keys", skip_all)]
+pub async fn revoke_api_keys(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Json(req): Json<BulkRevokeApiKeysRequest>,
+) -> Result<Json<BulkResponse>, AdminError> {
+    let services = get_services(&state)?;
+    let actor = AuditActor::from(&admin_auth);
+
+    // Every key shares the same owner, so the owner-scoped check runs up
+    // front (against a nil key id) and rejects the whole batch rather than
+    // reporting each key as forbidden.
+    check_owner_modify_authz(services, &authz, "delete", Uuid::nil(), &req.owner).await?;
+
+    let now = Utc::now();
+    let keys: Vec<ApiKey> = services
+        .api_keys
+        .list_all_by_owner(&req.owner)
+        .await?
+        .into_iter()
+        .filter(|key| req.matches(key, now))
+        .collect();
+
+    let mut response = BulkResponse::default();
+    for key in keys {
+        if req.dry_run {
+            response.push(Some(key.id), Ok(BulkItemStatus::Skipped));
+            continue;
+        }
+
+        let outcome = async {
+            check_owner_modify_authz(services, &authz, "delete", key.id, &key.owner).await?;
+            services.api_keys.revoke(key.id).await?;
+            Ok::<_, AdminError>(())
+        }
+        .await;
+        if outcome.is_ok() {
+            let (org_id, project_id) = match &key.owner {
+                ApiKeyOwner::Organization { org_id } => (Some(*org_id), None),
+                ApiKeyOwner::Project { project_id } => (None, Some(*project_id)),
+                _ => (None, None),
+            };
+            let _ = services
+                .audit_logs
+                .create(CreateAuditLog {
+                    actor_type: actor.actor_type,
+                    actor_id: actor.actor_id,
+                    action: "api_key.revoke".to_string(),
+                    resource_type: "api_key".to_string(),
+                    resource_id: key.id,
+                    org_id,
+                    project_id,
+                    details: json!({
+                        "name": key.name,
+                        "key_prefix": key.key_prefix,
+                        "bulk": true,
+                    }),
+                    ip_address: client_info.ip_address.clone(),
+                    user_agent: client_info.user_agent.clone(),
+                })
+                .await;
+
+            if let Some(cache) = &state.cache {
+                invalidate_api_key_cache(cache.as_ref(), key.id).await;
+            }
+        }
+        response.push(Some(key.id), outcome.map(|_| BulkItemStatus::Succeeded));
+    }
+
+    tracing::info!(
+        succeeded = response.succeeded,
+        failed = response.failed,
+        skipped = response.skipped,
+        dry_run = req.dry_run,
+        "Bulk API key revoke via admin API"
+    );
+
+    Ok(Json(response))
+}
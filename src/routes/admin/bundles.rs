@@ -0,0 +1,176 @@
+//! Bundle export and import endpoints.
+//!
+//! Export dumps organizations, teams, projects, memberships, API key
+//! metadata, RBAC policies and model pricing as one JSON or YAML document.
+//! Import restores such a bundle, typically into another environment or as
+//! part of a disaster recovery drill. Use `dry_run=true` to validate a bundle
+//! and see what it would change first.
+
+use axum::{
+    Extension, Json,
+    body::Bytes,
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use serde_json::json;
+use uuid::Uuid;
+
+use super::{AdminError, AuditActor};
+use crate::{
+    AppState,
+    middleware::{AdminAuth, AuthzContext, ClientInfo},
+    models::{
+        Bundle, BundleFormat, CreateAuditLog, ExportBundleQuery, ImportAction, ImportBundleQuery,
+        ImportReport,
+    },
+    services::Services,
+};
+
+fn get_services(state: &AppState) -> Result<&Services, AdminError> {
+    state.services.as_ref().ok_or(AdminError::ServicesRequired)
+}
+
+/// Whether a request body is YAML, based on its content type.
+fn is_yaml(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.split(';').next().unwrap_or_default().trim())
+        .is_some_and(|v| matches!(v, "application/yaml" | "application/x-yaml" | "text/yaml"))
+}
+
+/// Export a bundle
+///
+/// Returns organizations with their teams, projects, members, API key
+/// metadata, RBAC policies and model pricing, plus global model pricing.
+/// API key secrets and hashes are never exported, and revoked keys are left
+/// out.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/export",
+    tag = "bundles",
+    operation_id = "bundle_export",
+    params(ExportBundleQuery),
+    responses(
+        (status = 200, description = "Bundle (JSON or YAML based on format parameter)", body = Bundle),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn export(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Query(query): Query<ExportBundleQuery>,
+) -> Result<Response, AdminError> {
+    authz.require("system", "export", None, None, None, None)?;
+
+    let services = get_services(&state)?;
+    let slugs: Option<Vec<String>> = query.orgs.as_deref().map(|orgs| {
+        orgs.split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(String::from)
+            .collect()
+    });
+    let bundle = services.bundles.export(slugs.as_deref()).await?;
+
+    match query.format {
+        BundleFormat::Json => Ok(Json(bundle).into_response()),
+        BundleFormat::Yaml => {
+            let body = serde_yaml_ng::to_string(&bundle)
+                .map_err(|e| AdminError::Internal(e.to_string()))?;
+            Ok((
+                StatusCode::OK,
+                [(header::CONTENT_TYPE, "application/yaml")],
+                body,
+            )
+                .into_response())
+        }
+    }
+}
+
+/// Import a bundle
+///
+/// Creates the entities in the bundle that don't exist yet and updates the
+/// ones that differ, matching them by slug, name or external ID. Nothing
+/// missing from the bundle is deleted, so importing the same bundle twice is
+/// safe. API keys are reported but not imported, since their secrets aren't
+/// part of the bundle.
+///
+/// The whole bundle is validated before anything is written; a bundle with
+/// any error is rejected with all of its errors. Send YAML with a
+/// `Content-Type` of `application/yaml`, otherwise the body is read as JSON.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/import",
+    tag = "bundles",
+    operation_id = "bundle_import",
+    params(ImportBundleQuery),
+    request_body = Bundle,
+    responses(
+        (status = 200, description = "Changes made, or planned with dry_run", body = ImportReport),
+        (status = 400, description = "Invalid bundle", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn import(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Query(query): Query<ImportBundleQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<ImportReport>, AdminError> {
+    authz.require("system", "import", None, None, None, None)?;
+
+    let services = get_services(&state)?;
+    let bundle: Bundle = if is_yaml(&headers) {
+        serde_yaml_ng::from_slice(&body).map_err(|e| AdminError::BadRequest(e.to_string()))?
+    } else {
+        serde_json::from_slice(&body).map_err(|e| AdminError::BadRequest(e.to_string()))?
+    };
+    let org_slugs: Vec<_> = bundle
+        .organizations
+        .iter()
+        .map(|o| o.slug.clone())
+        .collect();
+
+    let actor = AuditActor::from(&admin_auth);
+    let report = services
+        .bundles
+        .import(
+            bundle,
+            query.dry_run,
+            actor.actor_id,
+            state.policy_registry.as_ref().map(|v| v.as_ref()),
+        )
+        .await?;
+
+    if !query.dry_run {
+        let _ = services
+            .audit_logs
+            .create(CreateAuditLog {
+                actor_type: actor.actor_type,
+                actor_id: actor.actor_id,
+                action: "bundle.import".to_string(),
+                resource_type: "bundle".to_string(),
+                resource_id: Uuid::nil(),
+                org_id: None,
+                project_id: None,
+                details: json!({
+                    "organizations": org_slugs,
+                    "created": report.count(ImportAction::Create),
+                    "updated": report.count(ImportAction::Update),
+                    "unchanged": report.count(ImportAction::Unchanged),
+                    "skipped": report.count(ImportAction::Skip),
+                }),
+                ip_address: client_info.ip_address,
+                user_agent: client_info.user_agent,
+            })
+            .await;
+    }
+
+    Ok(Json(report))
+}
@@ -0,0 +1,266 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use axum_valid::Valid;
+use serde::Serialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use super::{AuditActor, error::AdminError, organizations::ListQuery};
+use crate::{
+    AppState,
+    middleware::{AdminAuth, AuthzContext, ClientInfo},
+    models::{ClientCertMapping, CreateAuditLog, CreateClientCertMapping, ServiceAccount},
+    openapi::PaginationMeta,
+    services::Services,
+};
+
+/// Paginated list of client certificate mappings
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ClientCertMappingListResponse {
+    /// List of mappings
+    pub data: Vec<ClientCertMapping>,
+    /// Pagination metadata
+    pub pagination: PaginationMeta,
+}
+
+fn get_services(state: &AppState) -> Result<&Services, AdminError> {
+    state.services.as_ref().ok_or(AdminError::ServicesRequired)
+}
+
+/// Resolve the org and service account from path slugs.
+async fn get_service_account(
+    services: &Services,
+    org_slug: &str,
+    sa_slug: &str,
+) -> Result<ServiceAccount, AdminError> {
+    let org = services
+        .organizations
+        .get_by_slug(org_slug)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("Organization '{}' not found", org_slug)))?;
+
+    services
+        .service_accounts
+        .get_by_slug(org.id, sa_slug)
+        .await?
+        .ok_or_else(|| {
+            AdminError::NotFound(format!(
+                "Service account '{}' not found in organization '{}'",
+                sa_slug, org_slug
+            ))
+        })
+}
+
+/// Map a client certificate to a service account
+///
+/// Requests presenting a certificate that matches the mapping over mTLS
+/// authenticate as the service account. Match values are normalized before
+/// storage (fingerprints lowercased without colons, DNS names and emails
+/// lowercased).
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/organizations/{org_slug}/service-accounts/{sa_slug}/client-certs",
+    tag = "service_accounts",
+    operation_id = "client_cert_mapping_create",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("sa_slug" = String, Path, description = "Service account slug"),
+    ),
+    request_body = CreateClientCertMapping,
+    responses(
+        (status = 201, description = "Mapping created", body = ClientCertMapping),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization or service account not found", body = crate::openapi::ErrorResponse),
+        (status = 409, description = "Identifier is already mapped", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.client_cert_mappings.create", skip(state, admin_auth, authz, input), fields(%org_slug, %sa_slug))]
+pub async fn create(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path((org_slug, sa_slug)): Path<(String, String)>,
+    Valid(Json(input)): Valid<Json<CreateClientCertMapping>>,
+) -> Result<(StatusCode, Json<ClientCertMapping>), AdminError> {
+    let services = get_services(&state)?;
+    let actor = AuditActor::from(&admin_auth);
+    let sa = get_service_account(services, &org_slug, &sa_slug).await?;
+
+    // Mapping a certificate grants the service account's roles, so it needs
+    // the same permission as editing the account itself
+    authz.require(
+        "service_account",
+        "update",
+        Some(&sa.id.to_string()),
+        Some(&sa.org_id.to_string()),
+        None,
+        None,
+    )?;
+
+    if input.match_type.normalize(&input.match_value).is_empty() {
+        return Err(AdminError::BadRequest(
+            "match_value must not be blank".to_string(),
+        ));
+    }
+
+    let mapping = services
+        .client_cert_mappings
+        .create(sa.org_id, sa.id, input)
+        .await?;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "service_account.client_cert.create".to_string(),
+            resource_type: "service_account".to_string(),
+            resource_id: sa.id,
+            org_id: Some(sa.org_id),
+            project_id: None,
+            details: json!({
+                "mapping_id": mapping.id,
+                "match_type": mapping.match_type,
+                "match_value": mapping.match_value,
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok((StatusCode::CREATED, Json(mapping)))
+}
+
+/// List client certificate mappings for a service account
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/organizations/{org_slug}/service-accounts/{sa_slug}/client-certs",
+    tag = "service_accounts",
+    operation_id = "client_cert_mapping_list",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("sa_slug" = String, Path, description = "Service account slug"),
+        ListQuery,
+    ),
+    responses(
+        (status = 200, description = "List of mappings", body = ClientCertMappingListResponse),
+        (status = 400, description = "Invalid cursor or direction", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization or service account not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.client_cert_mappings.list", skip(state, authz, query), fields(%org_slug, %sa_slug))]
+pub async fn list(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path((org_slug, sa_slug)): Path<(String, String)>,
+    Query(query): Query<ListQuery>,
+) -> Result<Json<ClientCertMappingListResponse>, AdminError> {
+    let services = get_services(&state)?;
+    let sa = get_service_account(services, &org_slug, &sa_slug).await?;
+
+    authz.require(
+        "service_account",
+        "read",
+        Some(&sa.id.to_string()),
+        Some(&sa.org_id.to_string()),
+        None,
+        None,
+    )?;
+
+    let limit = query.limit.unwrap_or(100);
+    let params = query.try_into_with_cursor()?;
+
+    let result = services
+        .client_cert_mappings
+        .list_by_service_account(sa.id, params)
+        .await?;
+
+    let pagination = PaginationMeta::with_cursors(
+        limit,
+        result.has_more,
+        result.cursors.next.map(|c| c.encode()),
+        result.cursors.prev.map(|c| c.encode()),
+    );
+
+    Ok(Json(ClientCertMappingListResponse {
+        data: result.items,
+        pagination,
+    }))
+}
+
+/// Remove a client certificate mapping
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    delete,
+    path = "/admin/v1/organizations/{org_slug}/service-accounts/{sa_slug}/client-certs/{id}",
+    tag = "service_accounts",
+    operation_id = "client_cert_mapping_delete",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("sa_slug" = String, Path, description = "Service account slug"),
+        ("id" = Uuid, Path, description = "Mapping ID"),
+    ),
+    responses(
+        (status = 200, description = "Mapping deleted"),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Mapping not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.client_cert_mappings.delete", skip(state, admin_auth, authz), fields(%org_slug, %sa_slug, %id))]
+pub async fn delete(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path((org_slug, sa_slug, id)): Path<(String, String, Uuid)>,
+) -> Result<Json<()>, AdminError> {
+    let services = get_services(&state)?;
+    let actor = AuditActor::from(&admin_auth);
+    let sa = get_service_account(services, &org_slug, &sa_slug).await?;
+
+    authz.require(
+        "service_account",
+        "update",
+        Some(&sa.id.to_string()),
+        Some(&sa.org_id.to_string()),
+        None,
+        None,
+    )?;
+
+    // Only delete mappings that belong to the service account in the path
+    let mapping = services
+        .client_cert_mappings
+        .get_by_id(id)
+        .await?
+        .filter(|m| m.service_account_id == sa.id)
+        .ok_or_else(|| AdminError::NotFound(format!("Client cert mapping '{}' not found", id)))?;
+
+    services.client_cert_mappings.delete(mapping.id).await?;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "service_account.client_cert.delete".to_string(),
+            resource_type: "service_account".to_string(),
+            resource_id: sa.id,
+            org_id: Some(sa.org_id),
+            project_id: None,
+            details: json!({
+                "mapping_id": mapping.id,
+                "match_type": mapping.match_type,
+                "match_value": mapping.match_value,
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(Json(()))
+}
@@ -1,24 +1,35 @@
+use std::collections::HashMap;
+
 use axum::{
     Extension, Json,
+    body::Bytes,
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
 };
 use axum_valid::Valid;
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use super::{error::AdminError, organizations::ListQuery};
 use crate::{
     AppState,
+    api_types::chat_completion,
     middleware::AuthzContext,
     models::{
-        AppendMessages, Conversation, ConversationOwnerType, ConversationWithProject,
-        CreateConversation, Message, SetPinOrder, UpdateConversation,
+        AppendMessages, Conversation, ConversationBranch, ConversationOwner, ConversationOwnerType,
+        ConversationWithProject, CreateConversation, FileId, ForkConversation, Message,
+        SetPinOrder, SwitchConversationBranch, UpdateConversation, VectorStoreOwnerType,
     },
     openapi::PaginationMeta,
-    services::Services,
+    routing::{RoutedProvider, route_model_extended},
+    services::{ConversationService, Services},
 };
 
+/// Cap on attachments per message, to bound the file lookups per write.
+const MAX_ATTACHMENTS_PER_MESSAGE: usize = 20;
+
 /// Scope tuple for `authz.require` derived from a conversation's owner.
 struct ConversationAuthzScope {
     project: Option<String>,
@@ -49,6 +60,55 @@ fn get_services(state: &AppState) -> Result<&Services, AdminError> {
     state.services.as_ref().ok_or(AdminError::ServicesRequired)
 }
 
+/// Check that every attachment references a file owned by the conversation's
+/// owner, and fill in its filename and content type from the file record.
+///
+/// Attachments are served to anyone who can read the conversation, so files
+/// from any other scope are rejected rather than exposed.
+async fn resolve_attachments(
+    services: &Services,
+    owner: &ConversationOwner,
+    messages: &mut [Message],
+) -> Result<(), AdminError> {
+    let owner_type = match owner {
+        ConversationOwner::Project { .. } => VectorStoreOwnerType::Project,
+        ConversationOwner::User { .. } => VectorStoreOwnerType::User,
+    };
+    for message in messages {
+        if message.attachments.len() > MAX_ATTACHMENTS_PER_MESSAGE {
+            return Err(AdminError::Validation(format!(
+                "A message can have at most {MAX_ATTACHMENTS_PER_MESSAGE} attachments"
+            )));
+        }
+        for attachment in &mut message.attachments {
+            let file = services
+                .files
+                .get_for_owner(attachment.file_id, owner_type, owner.owner_id())
+                .await?
+                .ok_or_else(|| {
+                    AdminError::Validation(format!(
+                        "Attachment '{}' does not reference a file owned by the conversation owner",
+                        FileId::from(attachment.file_id)
+                    ))
+                })?;
+            attachment.filename = Some(file.filename);
+            attachment.content_type = file.content_type;
+        }
+    }
+    Ok(())
+}
+
+fn conversation_owner(c: &Conversation) -> ConversationOwner {
+    match c.owner_type {
+        ConversationOwnerType::Project => ConversationOwner::Project {
+            project_id: c.owner_id,
+        },
+        ConversationOwnerType::User => ConversationOwner::User {
+            user_id: c.owner_id,
+        },
+    }
+}
+
 /// Create a new conversation
 #[cfg_attr(feature = "utoipa", utoipa::path(
     post,
@@ -64,7 +124,7 @@ fn get_services(state: &AppState) -> Result<&Services, AdminError> {
 pub async fn create(
     State(state): State<AppState>,
     Extension(authz): Extension<AuthzContext>,
-    Valid(Json(input)): Valid<Json<CreateConversation>>,
+    Valid(Json(mut input)): Valid<Json<CreateConversation>>,
 ) -> Result<(StatusCode, Json<Conversation>), AdminError> {
     let services = get_services(&state)?;
 
@@ -125,6 +185,8 @@ pub async fn create(
         }
     }
 
+    resolve_attachments(services, &input.owner, &mut input.messages).await?;
+
     let conversation = services.conversations.create(input).await?;
     Ok((StatusCode::CREATED, Json(conversation)))
 }
@@ -389,7 +451,7 @@ pub async fn update(
     State(state): State<AppState>,
     Extension(authz): Extension<AuthzContext>,
     Path(id): Path<Uuid>,
-    Valid(Json(input)): Valid<Json<UpdateConversation>>,
+    Valid(Json(mut input)): Valid<Json<UpdateConversation>>,
 ) -> Result<Json<Conversation>, AdminError> {
     let services = get_services(&state)?;
 
@@ -434,6 +496,14 @@ pub async fn update(
         }
     }
 
+    if let Some(messages) = input.messages.as_mut() {
+        let owner = input
+            .owner
+            .clone()
+            .unwrap_or_else(|| conversation_owner(&existing));
+        resolve_attachments(services, &owner, messages).await?;
+    }
+
     let updated = services.conversations.update(id, input).await?;
     Ok(Json(updated))
 }
@@ -455,7 +525,7 @@ pub async fn append_messages(
     State(state): State<AppState>,
     Extension(authz): Extension<AuthzContext>,
     Path(id): Path<Uuid>,
-    Valid(Json(input)): Valid<Json<AppendMessages>>,
+    Valid(Json(mut input)): Valid<Json<AppendMessages>>,
 ) -> Result<Json<Vec<Message>>, AdminError> {
     let services = get_services(&state)?;
 
@@ -475,6 +545,13 @@ pub async fn append_messages(
         scope.project.as_deref(),
     )?;
 
+    resolve_attachments(
+        services,
+        &conversation_owner(&conversation),
+        &mut input.messages,
+    )
+    .await?;
+
     let messages = services.conversations.append_messages(id, input).await?;
     Ok(Json(messages))
 }
@@ -564,3 +641,362 @@ pub async fn set_pin(
         .await?;
     Ok(Json(updated))
 }
+
+/// List a conversation's branches
+///
+/// Branches are created by editing or deleting a message. Conversations that
+/// have never been forked have no branches.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/conversations/{id}/branches",
+    tag = "conversations",
+    operation_id = "conversation_branch_list",
+    params(("id" = Uuid, Path, description = "Conversation ID")),
+    responses(
+        (status = 200, description = "Branches, oldest first", body = Vec<ConversationBranch>),
+        (status = 404, description = "Conversation not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn list_branches(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<ConversationBranch>>, AdminError> {
+    let services = get_services(&state)?;
+
+    let conversation = services
+        .conversations
+        .get_by_id(id)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("Conversation '{}' not found", id)))?;
+    let id_str = id.to_string();
+    let scope = conversation_authz_scope(&conversation);
+    authz.require(
+        "conversation",
+        "read",
+        Some(&id_str),
+        None,
+        None,
+        scope.project.as_deref(),
+    )?;
+
+    let branches = services.conversations.list_branches(id).await?;
+    Ok(Json(branches))
+}
+
+/// Fork a conversation by editing or deleting a message
+///
+/// Provide `message` to replace the message at `message_index`, or omit it to
+/// delete that message and everything after it. The new branch becomes active
+/// and the conversation's messages are replaced with the branch history; the
+/// previous history stays available as the parent branch.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/conversations/{id}/branches",
+    tag = "conversations",
+    operation_id = "conversation_branch_fork",
+    params(("id" = Uuid, Path, description = "Conversation ID")),
+    request_body = ForkConversation,
+    responses(
+        (status = 201, description = "Branch created and activated", body = ConversationBranch),
+        (status = 400, description = "Message index out of range", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Conversation not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn fork(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(id): Path<Uuid>,
+    Valid(Json(mut input)): Valid<Json<ForkConversation>>,
+) -> Result<(StatusCode, Json<ConversationBranch>), AdminError> {
+    let services = get_services(&state)?;
+
+    let conversation = services
+        .conversations
+        .get_by_id(id)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("Conversation '{}' not found", id)))?;
+    let id_str = id.to_string();
+    let scope = conversation_authz_scope(&conversation);
+    authz.require(
+        "conversation",
+        "update",
+        Some(&id_str),
+        None,
+        None,
+        scope.project.as_deref(),
+    )?;
+
+    if let Some(message) = input.message.as_mut() {
+        resolve_attachments(
+            services,
+            &conversation_owner(&conversation),
+            std::slice::from_mut(message),
+        )
+        .await?;
+    }
+
+    let branch = services.conversations.fork(id, input).await?;
+    Ok((StatusCode::CREATED, Json(branch)))
+}
+
+/// Switch a conversation to another branch
+///
+/// Replaces the conversation's messages with the branch history. Subsequent
+/// appends and edits apply to this branch.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    put,
+    path = "/admin/v1/conversations/{id}/branches/active",
+    tag = "conversations",
+    operation_id = "conversation_branch_switch",
+    params(("id" = Uuid, Path, description = "Conversation ID")),
+    request_body = SwitchConversationBranch,
+    responses(
+        (status = 200, description = "Branch activated", body = Conversation),
+        (status = 404, description = "Conversation or branch not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn switch_branch(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(id): Path<Uuid>,
+    Valid(Json(input)): Valid<Json<SwitchConversationBranch>>,
+) -> Result<Json<Conversation>, AdminError> {
+    let services = get_services(&state)?;
+
+    let conversation = services
+        .conversations
+        .get_by_id(id)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("Conversation '{}' not found", id)))?;
+    let id_str = id.to_string();
+    let scope = conversation_authz_scope(&conversation);
+    authz.require(
+        "conversation",
+        "update",
+        Some(&id_str),
+        None,
+        None,
+        scope.project.as_deref(),
+    )?;
+
+    let updated = services
+        .conversations
+        .switch_branch(id, input.branch_id)
+        .await
+        .map_err(|e| match e {
+            crate::db::DbError::NotFound => {
+                AdminError::NotFound(format!("Branch '{}' not found", input.branch_id))
+            }
+            e => e.into(),
+        })?;
+    Ok(Json(updated))
+}
+
+/// Download a file attached to a conversation
+///
+/// Anyone who can read the conversation can read its attachments. The file
+/// must be attached to one of the conversation's current messages.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/conversations/{id}/attachments/{file_id}",
+    tag = "conversations",
+    operation_id = "conversation_attachment_get",
+    params(
+        ("id" = Uuid, Path, description = "Conversation ID"),
+        ("file_id" = String, Path, description = "File ID"),
+    ),
+    responses(
+        (status = 200, description = "File content", content_type = "application/octet-stream"),
+        (status = 404, description = "Conversation or attachment not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn get_attachment(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path((id, file_id)): Path<(Uuid, FileId)>,
+) -> Result<Response, AdminError> {
+    let services = get_services(&state)?;
+    let file_id = file_id.into_inner();
+
+    let conversation = services
+        .conversations
+        .get_by_id(id)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("Conversation '{}' not found", id)))?;
+    let id_str = id.to_string();
+    let scope = conversation_authz_scope(&conversation);
+    authz.require(
+        "conversation",
+        "read",
+        Some(&id_str),
+        None,
+        None,
+        scope.project.as_deref(),
+    )?;
+
+    let not_found =
+        || AdminError::NotFound(format!("Attachment '{}' not found", FileId::from(file_id)));
+    let attached = conversation
+        .messages
+        .iter()
+        .flat_map(|m| &m.attachments)
+        .any(|a| a.file_id == file_id);
+    if !attached {
+        return Err(not_found());
+    }
+
+    let file = services.files.get(file_id).await?.ok_or_else(not_found)?;
+    let content = services.files.get_content(file_id).await.map_err(|e| {
+        tracing::error!(file_id = %file_id, error = %e, "Failed to read attachment content");
+        AdminError::Internal("Failed to read attachment".to_string())
+    })?;
+
+    let content_type = file
+        .content_type
+        .unwrap_or_else(|| "application/octet-stream".to_string());
+    let filename = file.filename.replace(['"', '\\', '\r', '\n'], "_");
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, content_type),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("inline; filename=\"{filename}\""),
+            ),
+        ],
+        Bytes::from(content),
+    )
+        .into_response())
+}
+
+/// Query parameters for replaying a conversation
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::IntoParams))]
+pub struct ReplayQuery {
+    /// Model the conversation will be sent to (e.g. `openai/gpt-4o`)
+    pub model: String,
+}
+
+/// A conversation rendered as Chat Completions messages
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ConversationReplay {
+    pub model: String,
+    /// Whether the model accepts images. When true, image attachments are
+    /// inlined as `image_url` parts.
+    pub vision: bool,
+    /// Messages ready to send as `messages` to `/v1/chat/completions`
+    pub messages: Vec<chat_completion::Message>,
+}
+
+/// Replay a conversation to a model
+///
+/// Returns the conversation's messages in Chat Completions format. Image
+/// attachments are inlined as data URLs when the model supports vision
+/// (according to its model config or the model catalog); other attachments,
+/// and images larger than `features.image_fetching.max_size_mb`, are
+/// mentioned by filename instead.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/conversations/{id}/replay",
+    tag = "conversations",
+    operation_id = "conversation_replay",
+    params(("id" = Uuid, Path, description = "Conversation ID"), ReplayQuery),
+    responses(
+        (status = 200, description = "Replayable messages", body = ConversationReplay),
+        (status = 404, description = "Conversation not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn replay(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<ReplayQuery>,
+) -> Result<Json<ConversationReplay>, AdminError> {
+    let services = get_services(&state)?;
+
+    let conversation = services
+        .conversations
+        .get_by_id(id)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("Conversation '{}' not found", id)))?;
+    let id_str = id.to_string();
+    let scope = conversation_authz_scope(&conversation);
+    authz.require(
+        "conversation",
+        "read",
+        Some(&id_str),
+        None,
+        None,
+        scope.project.as_deref(),
+    )?;
+
+    let vision = model_supports_vision(&state, &query.model);
+    let mut inline_images = HashMap::new();
+    if vision {
+        let max_bytes =
+            (state.config.features.image_fetching.max_size_mb as i64).saturating_mul(1024 * 1024);
+        for attachment in conversation
+            .messages
+            .iter()
+            .filter(|m| m.role == "user")
+            .flat_map(|m| &m.attachments)
+            .filter(|a| a.is_image())
+        {
+            let Some(file) = services.files.get(attachment.file_id).await? else {
+                continue;
+            };
+            if file.size_bytes > max_bytes {
+                continue;
+            }
+            match services.files.get_content(attachment.file_id).await {
+                Ok(content) => {
+                    let content_type = file.content_type.as_deref().unwrap_or("image/png");
+                    inline_images.insert(
+                        attachment.file_id,
+                        format!("data:{content_type};base64,{}", BASE64.encode(content)),
+                    );
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        file_id = %attachment.file_id,
+                        error = %e,
+                        "Failed to read image attachment for replay"
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(Json(ConversationReplay {
+        model: query.model,
+        vision,
+        messages: ConversationService::replay_messages(&conversation.messages, &inline_images),
+    }))
+}
+
+/// Whether `model` accepts image input, from its `[providers.*.models]`
+/// config or the model catalog. Dynamic providers are assumed not to.
+fn model_supports_vision(state: &AppState, model: &str) -> bool {
+    let Ok(RoutedProvider::Static(route)) =
+        route_model_extended(Some(model), &state.config.providers)
+    else {
+        return false;
+    };
+    if let Some(caps) = route
+        .provider_config
+        .get_model_config(&route.model)
+        .and_then(|mc| mc.capabilities.as_ref())
+    {
+        return caps.vision;
+    }
+    crate::catalog::resolve_catalog_provider_id(
+        route.provider_config.provider_type_name(),
+        route.provider_config.base_url(),
+        route.provider_config.catalog_provider(),
+    )
+    .and_then(|pid| state.model_catalog.lookup(&pid, &route.model))
+    .is_some_and(|e| e.capabilities.vision)
+}
@@ -451,10 +451,13 @@ mod tests {
     use uuid::Uuid;
 
     use super::*;
-    use crate::models::{
-        AccessInventorySummary, ApiKeySummary, OrgAccessEntry, OrgAccessReportSummary,
-        OrgMemberAccessEntry, StaleAccessSummary, StaleApiKeyEntry, StaleUserEntry,
-        UserAccessApiKeyEntry, UserAccessInventoryEntry, UserAccessOrgEntry, UserAccessSummary,
+    use crate::{
+        models::{
+            AccessInventorySummary, ApiKeySummary, OrgAccessEntry, OrgAccessReportSummary,
+            OrgMemberAccessEntry, StaleAccessSummary, StaleApiKeyEntry, StaleUserEntry,
+            UserAccessApiKeyEntry, UserAccessInventoryEntry, UserAccessOrgEntry, UserAccessSummary,
+        },
+        openapi::PaginationMeta,
     };
 
     #[test]
@@ -470,6 +473,7 @@ mod tests {
                 total_project_memberships: 0,
                 total_active_api_keys: 0,
             },
+            pagination: PaginationMeta::with_cursors(100, false, None, None),
         };
 
         let csv = export_access_inventory_csv(&response).unwrap();
@@ -516,6 +520,7 @@ mod tests {
                 total_project_memberships: 0,
                 total_active_api_keys: 2,
             },
+            pagination: PaginationMeta::with_cursors(100, false, None, None),
         };
 
         let csv = export_access_inventory_csv(&response).unwrap();
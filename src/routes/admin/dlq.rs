@@ -13,7 +13,10 @@ use uuid::Uuid;
 use super::AdminError;
 use crate::{
     AppState,
-    dlq::{DeadLetterQueue, DlqCursor, DlqCursorDirection, DlqEntry, DlqListParams},
+    dlq::{
+        DeadLetterQueue, DlqCursor, DlqCursorDirection, DlqEntry, DlqListParams,
+        redrive::{self, DlqRedriveFilter, RedriveOutcome, RedriveSummary},
+    },
     middleware::AuthzContext,
     models::UsageLogEntry,
     observability::metrics,
@@ -149,6 +152,37 @@ pub struct DlqRetryResponse {
     pub message: String,
 }
 
+/// Changes to a DLQ entry's payload before it's retried. Set at most one
+/// field; the stored entry keeps its original payload unless the retry
+/// succeeds and removes it.
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct DlqRetryRequest {
+    /// Payload to replay instead of the stored one.
+    pub payload: Option<serde_json::Value>,
+    /// JSON merge patch (RFC 7386) applied to the stored payload.
+    pub patch: Option<serde_json::Value>,
+}
+
+/// Maximum entries redriven by one batch request.
+const MAX_REDRIVE_BATCH: usize = 1000;
+
+/// Batch redrive request.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct DlqRedriveRequest {
+    /// Which entries to redrive. An empty filter matches every entry.
+    #[serde(default)]
+    pub filter: DlqRedriveFilter,
+    /// JSON merge patch (RFC 7386) applied to each payload before it's replayed.
+    pub patch: Option<serde_json::Value>,
+    /// Maximum number of entries to redrive (default: 100, max: 1000).
+    pub limit: Option<usize>,
+    /// Only report the matching entries, without redriving them.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
 fn get_dlq(state: &AppState) -> Result<&std::sync::Arc<dyn DeadLetterQueue>, AdminError> {
     state
         .dlq
@@ -351,6 +385,10 @@ pub async fn delete(
 }
 
 /// Retry a specific DLQ entry.
+///
+/// The request body can replace or patch the payload before it's replayed.
+/// An edited payload is authorized against its own tenant scope as well as
+/// the stored entry's.
 #[cfg_attr(feature = "utoipa", utoipa::path(
     post,
     path = "/admin/v1/dlq/{id}/retry",
@@ -359,6 +397,7 @@ pub async fn delete(
     params(
         ("id" = Uuid, Path, description = "DLQ entry ID"),
     ),
+    request_body = DlqRetryRequest,
     responses(
         (status = 200, description = "Retry result", body = DlqRetryResponse),
         (status = 400, description = "DLQ not configured or invalid entry", body = crate::openapi::ErrorResponse),
@@ -369,6 +408,7 @@ pub async fn retry(
     State(state): State<AppState>,
     Extension(authz): Extension<AuthzContext>,
     Path(id): Path<Uuid>,
+    body: Option<Json<DlqRetryRequest>>,
 ) -> Result<Json<DlqRetryResponse>, AdminError> {
     let dlq = get_dlq(&state)?;
     let db = state
@@ -392,40 +432,59 @@ pub async fn retry(
         None => return Err(AdminError::NotFound("DLQ entry".to_string())),
     };
 
-    // Process based on entry type. Every type that materialises tenant work
-    // back into the database must add an arm here.
-    let result = match entry.entry_type.as_str() {
-        "usage_log" => {
-            let usage_entry: UsageLogEntry = serde_json::from_str(&entry.payload)
-                .map_err(|e| AdminError::BadRequest(format!("Invalid usage_log payload: {}", e)))?;
+    // Every type that materialises tenant work back into the database must
+    // be supported by the redrive module.
+    if !redrive::is_supported(&entry.entry_type) {
+        return Err(AdminError::BadRequest(format!(
+            "Unsupported entry type for manual retry: {}",
+            entry.entry_type
+        )));
+    }
 
-            // Try to write to database
-            match db.usage().log(usage_entry).await {
-                Ok(_) => {
-                    // Success - remove from queue
-                    if let Err(e) = dlq.remove(id).await {
-                        tracing::error!(error = %e, entry_id = %id, "Failed to remove successfully retried entry");
-                    }
-                    metrics::record_dlq_operation("manual_retry_success", &entry.entry_type);
-                    DlqRetryResponse {
-                        success: true,
-                        message: "Entry processed and removed from queue".to_string(),
-                    }
-                }
-                Err(e) => {
-                    // Failed - mark as retried
-                    if let Err(mark_err) = dlq.mark_retried(id).await {
-                        tracing::error!(error = %mark_err, entry_id = %id, "Failed to mark entry as retried");
-                    }
-                    metrics::record_dlq_operation("manual_retry_failure", &entry.entry_type);
-                    DlqRetryResponse {
-                        success: false,
-                        message: format!("Retry failed: {}", e),
-                    }
-                }
+    let request = body.map(|Json(request)| request).unwrap_or_default();
+    let edited = match (request.payload, request.patch) {
+        (Some(_), Some(_)) => {
+            return Err(AdminError::BadRequest(
+                "Set either payload or patch, not both".to_string(),
+            ));
+        }
+        (Some(payload), None) => Some(DlqEntry {
+            payload: payload.to_string(),
+            ..entry.clone()
+        }),
+        (None, Some(patch)) => {
+            Some(redrive::patch_entry(&entry, &patch).map_err(AdminError::BadRequest)?)
+        }
+        (None, None) => None,
+    };
+    let entry = match edited {
+        Some(edited) => {
+            // The edit could move the work to another tenant, so the caller
+            // needs access to the tenant it ends up in too.
+            require_entry_authz(&authz, "update", Some(&edited)).await?;
+            edited
+        }
+        None => entry,
+    };
+
+    redrive::validate_payload(&entry).map_err(AdminError::BadRequest)?;
+
+    let result = match redrive::redrive_entry(dlq, db, &entry, "manual").await {
+        RedriveOutcome::Succeeded => {
+            metrics::record_dlq_operation("manual_retry_success", &entry.entry_type);
+            DlqRetryResponse {
+                success: true,
+                message: "Entry processed and removed from queue".to_string(),
             }
         }
-        _ => {
+        RedriveOutcome::Failed(e) => {
+            metrics::record_dlq_operation("manual_retry_failure", &entry.entry_type);
+            DlqRetryResponse {
+                success: false,
+                message: format!("Retry failed: {}", e),
+            }
+        }
+        RedriveOutcome::Unsupported => {
             return Err(AdminError::BadRequest(format!(
                 "Unsupported entry type for manual retry: {}",
                 entry.entry_type
@@ -436,6 +495,71 @@ pub async fn retry(
     Ok(Json(result))
 }
 
+/// Redrive DLQ entries matching a filter.
+///
+/// Replays up to `limit` matching entries, oldest first, optionally merging
+/// a patch into each payload. Entries that replay successfully are removed;
+/// the rest stay queued with their retry count bumped. Entries can belong to
+/// any tenant, so this requires platform-level `dlq:update`.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/dlq/redrive",
+    tag = "dlq",
+    operation_id = "dlq_redrive",
+    request_body = DlqRedriveRequest,
+    responses(
+        (status = 200, description = "Redrive result", body = RedriveSummary),
+        (status = 400, description = "DLQ not configured or invalid request", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn redrive(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Json(request): Json<DlqRedriveRequest>,
+) -> Result<Json<RedriveSummary>, AdminError> {
+    authz.require("dlq", "update", None, None, None, None)?;
+    let dlq = get_dlq(&state)?;
+    let db = state
+        .db
+        .as_ref()
+        .ok_or_else(|| AdminError::BadRequest("Database is not configured".to_string()))?;
+
+    if let Some(patch) = &request.patch
+        && !patch.is_object()
+    {
+        return Err(AdminError::BadRequest(
+            "patch must be a JSON object".to_string(),
+        ));
+    }
+    let limit = request.limit.unwrap_or(100).clamp(1, MAX_REDRIVE_BATCH);
+
+    let summary = redrive::redrive_matching(
+        dlq,
+        db,
+        &request.filter,
+        request.patch.as_ref(),
+        limit,
+        request.dry_run,
+        "batch",
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "Failed to redrive DLQ entries");
+        AdminError::Internal(e.to_string())
+    })?;
+
+    tracing::info!(
+        matched = summary.matched,
+        succeeded = summary.succeeded,
+        failed = summary.failed,
+        skipped = summary.skipped,
+        dry_run = request.dry_run,
+        "DLQ redrive via admin API"
+    );
+
+    Ok(Json(summary))
+}
+
 /// Get DLQ statistics.
 #[cfg_attr(feature = "utoipa", utoipa::path(
     get,
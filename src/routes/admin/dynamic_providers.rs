@@ -8,13 +8,17 @@ use serde::{Deserialize, Serialize};
 use serde_json::json;
 use uuid::Uuid;
 
-use super::{AuditActor, error::AdminError, organizations::ListQuery};
+use super::{
+    AuditActor,
+    error::AdminError,
+    organizations::{LabelQuery, ListQuery},
+};
 use crate::{
     AppState,
     middleware::{AdminAuth, AuthzContext, ClientInfo},
     models::{
         ConnectivityTestResponse, CreateAuditLog, CreateDynamicProvider, DynamicProvider,
-        DynamicProviderResponse, ProviderOwner, UpdateDynamicProvider,
+        DynamicProviderResponse, ProviderOwner, SetLabels, UpdateDynamicProvider, validate_labels,
     },
     openapi::PaginationMeta,
     services::Services,
@@ -331,6 +335,80 @@ pub async fn update(
     Ok(Json(provider.into()))
 }
 
+/// Set a dynamic provider's labels
+///
+/// Replaces all of the provider's labels. Send an empty object to remove
+/// them.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    put,
+    path = "/admin/v1/dynamic-providers/{id}/labels",
+    tag = "dynamic-providers",
+    operation_id = "dynamic_provider_set_labels",
+    params(("id" = Uuid, Path, description = "Dynamic provider ID")),
+    request_body = SetLabels,
+    responses(
+        (status = 200, description = "Labels saved", body = DynamicProviderResponse),
+        (status = 400, description = "Invalid labels", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Dynamic provider not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn set_labels(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path(id): Path<Uuid>,
+    Json(input): Json<SetLabels>,
+) -> Result<Json<DynamicProviderResponse>, AdminError> {
+    let services = get_services(&state)?;
+
+    let existing = services
+        .providers
+        .get_by_id(id)
+        .await?
+        .ok_or_else(|| AdminError::NotFound("Dynamic provider not found".to_string()))?;
+
+    let (authz_org, authz_team, authz_project) = owner_authz_scope(&existing.owner, services).await;
+    authz.require(
+        "dynamic_provider",
+        "update",
+        Some(&id.to_string()),
+        authz_org.as_deref(),
+        authz_team.as_deref(),
+        authz_project.as_deref(),
+    )?;
+
+    let actor = AuditActor::from(&admin_auth);
+
+    validate_labels(&input.labels).map_err(AdminError::Validation)?;
+
+    let provider = services.providers.set_labels(id, &input.labels).await?;
+
+    let (org_id, project_id) = owner_audit_scope(&provider.owner, services).await;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "dynamic_provider.labels_update".to_string(),
+            resource_type: "dynamic_provider".to_string(),
+            resource_id: provider.id,
+            org_id,
+            project_id,
+            details: json!({
+                "name": provider.name,
+                "previous": existing.labels,
+                "labels": input.labels,
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(Json(provider.into()))
+}
+
 /// Delete a dynamic provider
 #[cfg_attr(feature = "utoipa", utoipa::path(
     delete,
@@ -415,10 +493,11 @@ pub async fn delete(
     params(
         ("org_slug" = String, Path, description = "Organization slug"),
         ListQuery,
+        LabelQuery,
     ),
     responses(
         (status = 200, description = "List of dynamic providers", body = DynamicProviderListResponse),
-        (status = 400, description = "Invalid cursor or direction", body = crate::openapi::ErrorResponse),
+        (status = 400, description = "Invalid cursor, direction or label selector", body = crate::openapi::ErrorResponse),
         (status = 404, description = "Organization not found", body = crate::openapi::ErrorResponse),
     )
 ))]
@@ -427,6 +506,7 @@ pub async fn list_by_org(
     Extension(authz): Extension<AuthzContext>,
     Path(org_slug): Path<String>,
     Query(query): Query<ListQuery>,
+    Query(labels): Query<LabelQuery>,
 ) -> Result<Json<DynamicProviderListResponse>, AdminError> {
     let services = get_services(&state)?;
 
@@ -447,7 +527,7 @@ pub async fn list_by_org(
     )?;
 
     let limit = query.limit.unwrap_or(100);
-    let params = query.try_into_with_cursor()?;
+    let params = labels.apply(query.try_into_with_cursor()?)?;
 
     let result = services.providers.list_by_org(org.id, params).await?;
 
@@ -474,10 +554,11 @@ pub async fn list_by_org(
         ("org_slug" = String, Path, description = "Organization slug"),
         ("project_slug" = String, Path, description = "Project slug"),
         ListQuery,
+        LabelQuery,
     ),
     responses(
         (status = 200, description = "List of dynamic providers", body = DynamicProviderListResponse),
-        (status = 400, description = "Invalid cursor or direction", body = crate::openapi::ErrorResponse),
+        (status = 400, description = "Invalid cursor, direction or label selector", body = crate::openapi::ErrorResponse),
         (status = 404, description = "Organization or project not found", body = crate::openapi::ErrorResponse),
     )
 ))]
@@ -486,6 +567,7 @@ pub async fn list_by_project(
     Extension(authz): Extension<AuthzContext>,
     Path((org_slug, project_slug)): Path<(String, String)>,
     Query(query): Query<ListQuery>,
+    Query(labels): Query<LabelQuery>,
 ) -> Result<Json<DynamicProviderListResponse>, AdminError> {
     let services = get_services(&state)?;
 
@@ -518,7 +600,7 @@ pub async fn list_by_project(
     )?;
 
     let limit = query.limit.unwrap_or(100);
-    let params = query.try_into_with_cursor()?;
+    let params = labels.apply(query.try_into_with_cursor()?)?;
 
     let result = services
         .providers
@@ -547,10 +629,11 @@ pub async fn list_by_project(
     params(
         ("user_id" = Uuid, Path, description = "User ID"),
         ListQuery,
+        LabelQuery,
     ),
     responses(
         (status = 200, description = "List of dynamic providers", body = DynamicProviderListResponse),
-        (status = 400, description = "Invalid cursor or direction", body = crate::openapi::ErrorResponse),
+        (status = 400, description = "Invalid cursor, direction or label selector", body = crate::openapi::ErrorResponse),
     )
 ))]
 pub async fn list_by_user(
@@ -558,6 +641,7 @@ pub async fn list_by_user(
     Extension(authz): Extension<AuthzContext>,
     Path(user_id): Path<Uuid>,
     Query(query): Query<ListQuery>,
+    Query(labels): Query<LabelQuery>,
 ) -> Result<Json<DynamicProviderListResponse>, AdminError> {
     let services = get_services(&state)?;
     // Resolve user's org for scope context (best-effort)
@@ -578,7 +662,7 @@ pub async fn list_by_user(
     )?;
 
     let limit = query.limit.unwrap_or(100);
-    let params = query.try_into_with_cursor()?;
+    let params = labels.apply(query.try_into_with_cursor()?)?;
 
     let result = services.providers.list_by_user(user_id, params).await?;
 
@@ -698,6 +782,7 @@ pub async fn test_credentials(
         models: input.models.unwrap_or_default(),
         sovereignty: input.sovereignty,
         is_enabled: true,
+        labels: Default::default(),
         created_at: chrono::Utc::now(),
         updated_at: chrono::Utc::now(),
     };
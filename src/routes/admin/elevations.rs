@@ -0,0 +1,479 @@
+//! Admin API endpoints for time-boxed role elevations (break-glass access).
+//!
+//! A member requests a role in their organization with a justification.
+//! Someone else with permission to update the organization approves or
+//! denies it; once approved, the member holds the role for the requested
+//! number of hours and the expiry worker restores their previous role
+//! afterwards. Every step is written to the audit log.
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use axum_valid::Valid;
+use serde::Serialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use super::{AuditActor, error::AdminError, organizations::ListQuery};
+use crate::{
+    AppState,
+    config::ElevationsConfig,
+    middleware::{AdminAuth, AuthzContext, ClientInfo},
+    models::{
+        CreateAuditLog, CreateRoleElevation, ElevationDecision, ElevationStatus, Organization,
+        RoleElevation,
+    },
+    openapi::PaginationMeta,
+    services::Services,
+};
+
+/// Paginated list of role elevations
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct RoleElevationListResponse {
+    /// List of role elevations
+    pub data: Vec<RoleElevation>,
+    /// Pagination metadata
+    pub pagination: PaginationMeta,
+}
+
+fn get_services(state: &AppState) -> Result<&Services, AdminError> {
+    state.services.as_ref().ok_or(AdminError::ServicesRequired)
+}
+
+fn get_config(state: &AppState) -> Result<&ElevationsConfig, AdminError> {
+    let config = &state.config.features.elevations;
+    if !config.enabled {
+        return Err(AdminError::NotConfigured(
+            "Role elevations are not enabled".to_string(),
+        ));
+    }
+    Ok(config)
+}
+
+/// Look up an organization and require `action` on it.
+async fn authorize_org(
+    services: &Services,
+    authz: &AuthzContext,
+    org_slug: &str,
+    action: &str,
+) -> Result<Organization, AdminError> {
+    let org = services
+        .organizations
+        .get_by_slug(org_slug)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("Organization '{}' not found", org_slug)))?;
+
+    require_org(authz, &org, action)?;
+    Ok(org)
+}
+
+fn require_org(authz: &AuthzContext, org: &Organization, action: &str) -> Result<(), AdminError> {
+    authz.require(
+        "organization",
+        action,
+        Some(&org.id.to_string()),
+        Some(&org.id.to_string()),
+        None,
+        None,
+    )?;
+    Ok(())
+}
+
+/// Load an elevation of `org`. Other organizations' elevations are reported
+/// as not found.
+async fn get_elevation(
+    services: &Services,
+    org: &Organization,
+    id: Uuid,
+) -> Result<RoleElevation, AdminError> {
+    services
+        .elevations
+        .get_by_id(id)
+        .await?
+        .filter(|e| e.org_id == org.id)
+        .ok_or_else(|| AdminError::NotFound(format!("Role elevation '{}' not found", id)))
+}
+
+/// Reject decisions on elevations that were already decided or withdrawn.
+fn ensure_pending(elevation: &RoleElevation) -> Result<(), AdminError> {
+    if elevation.status != ElevationStatus::Pending {
+        return Err(AdminError::Conflict(format!(
+            "Role elevation is already {}",
+            elevation.status.as_str()
+        )));
+    }
+    Ok(())
+}
+
+/// Request an elevated role
+///
+/// Files a pending request for one of `[features.elevations] roles`. Without
+/// `user_id` the request is for the caller; requesting for another member
+/// requires permission to update the organization. A member can have one
+/// pending or active elevation at a time.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/organizations/{org_slug}/elevations",
+    tag = "organizations",
+    operation_id = "role_elevation_create",
+    params(("org_slug" = String, Path, description = "Organization slug")),
+    request_body = CreateRoleElevation,
+    responses(
+        (status = 201, description = "Elevation requested", body = RoleElevation),
+        (status = 400, description = "Role not requestable, duration too long, or user not a member", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found", body = crate::openapi::ErrorResponse),
+        (status = 409, description = "The member already has a pending or active elevation", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "Role elevations are not enabled", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.elevations.create", skip(state, admin_auth, authz, input), fields(%org_slug))]
+pub async fn create(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path(org_slug): Path<String>,
+    Valid(Json(input)): Valid<Json<CreateRoleElevation>>,
+) -> Result<(StatusCode, Json<RoleElevation>), AdminError> {
+    let config = get_config(&state)?;
+    let services = get_services(&state)?;
+    let org = authorize_org(services, &authz, &org_slug, "read").await?;
+    let actor = AuditActor::from(&admin_auth);
+
+    let caller = admin_auth.identity.user_id;
+    let user_id = match (input.user_id, caller) {
+        (Some(user_id), _) => user_id,
+        (None, Some(caller)) => caller,
+        (None, None) => {
+            return Err(AdminError::BadRequest(
+                "user_id is required when the caller isn't linked to a user".to_string(),
+            ));
+        }
+    };
+    if Some(user_id) != caller {
+        require_org(&authz, &org, "update")?;
+    }
+
+    let elevation = services
+        .elevations
+        .request(config, org.id, user_id, input, actor.actor_id)
+        .await?;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "elevation.request".to_string(),
+            resource_type: "role_elevation".to_string(),
+            resource_id: elevation.id,
+            org_id: Some(org.id),
+            project_id: None,
+            details: json!({
+                "user_id": elevation.user_id,
+                "role": elevation.role,
+                "duration_hours": elevation.duration_hours,
+                "justification": elevation.justification,
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok((StatusCode::CREATED, Json(elevation)))
+}
+
+/// List an organization's role elevations
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/organizations/{org_slug}/elevations",
+    tag = "organizations",
+    operation_id = "role_elevation_list",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ListQuery,
+    ),
+    responses(
+        (status = 200, description = "List of role elevations", body = RoleElevationListResponse),
+        (status = 400, description = "Invalid cursor or direction", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "Role elevations are not enabled", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.elevations.list", skip(state, authz, query), fields(%org_slug))]
+pub async fn list(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(org_slug): Path<String>,
+    Query(query): Query<ListQuery>,
+) -> Result<Json<RoleElevationListResponse>, AdminError> {
+    get_config(&state)?;
+    let services = get_services(&state)?;
+    let org = authorize_org(services, &authz, &org_slug, "read").await?;
+
+    let limit = query.limit.unwrap_or(100);
+    let params = query.try_into_with_cursor()?;
+
+    let result = services.elevations.list_by_org(org.id, params).await?;
+
+    let pagination = PaginationMeta::with_cursors(
+        limit,
+        result.has_more,
+        result.cursors.next.map(|c| c.encode()),
+        result.cursors.prev.map(|c| c.encode()),
+    );
+
+    Ok(Json(RoleElevationListResponse {
+        data: result.items,
+        pagination,
+    }))
+}
+
+/// Get a role elevation
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/organizations/{org_slug}/elevations/{id}",
+    tag = "organizations",
+    operation_id = "role_elevation_get",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("id" = Uuid, Path, description = "Role elevation ID"),
+    ),
+    responses(
+        (status = 200, description = "Role elevation", body = RoleElevation),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Role elevation not found", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "Role elevations are not enabled", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.elevations.get", skip(state, authz), fields(%org_slug, %id))]
+pub async fn get(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path((org_slug, id)): Path<(String, Uuid)>,
+) -> Result<Json<RoleElevation>, AdminError> {
+    get_config(&state)?;
+    let services = get_services(&state)?;
+    let org = authorize_org(services, &authz, &org_slug, "read").await?;
+
+    Ok(Json(get_elevation(services, &org, id).await?))
+}
+
+/// Approve a role elevation
+///
+/// Grants the requested role until `duration_hours` from now, remembering
+/// the member's current role so it can be restored. Members can't approve
+/// their own elevation.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/organizations/{org_slug}/elevations/{id}/approve",
+    tag = "organizations",
+    operation_id = "role_elevation_approve",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("id" = Uuid, Path, description = "Role elevation ID"),
+    ),
+    request_body = ElevationDecision,
+    responses(
+        (status = 200, description = "Elevation approved and role granted", body = RoleElevation),
+        (status = 400, description = "The user is no longer a member", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Access denied, or approving your own elevation", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Role elevation not found", body = crate::openapi::ErrorResponse),
+        (status = 409, description = "Role elevation is no longer pending", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "Role elevations are not enabled", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.elevations.approve", skip(state, admin_auth, authz, input), fields(%org_slug, %id))]
+pub async fn approve(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path((org_slug, id)): Path<(String, Uuid)>,
+    Valid(Json(input)): Valid<Json<ElevationDecision>>,
+) -> Result<Json<RoleElevation>, AdminError> {
+    get_config(&state)?;
+    let services = get_services(&state)?;
+    let org = authorize_org(services, &authz, &org_slug, "update").await?;
+    let actor = AuditActor::from(&admin_auth);
+
+    let elevation = get_elevation(services, &org, id).await?;
+    ensure_pending(&elevation)?;
+    if admin_auth.identity.user_id == Some(elevation.user_id) {
+        return Err(AdminError::Forbidden(
+            "You can't approve your own role elevation".to_string(),
+        ));
+    }
+
+    let elevation = services
+        .elevations
+        .approve(
+            &elevation,
+            actor.actor_id,
+            input.note.as_deref(),
+            chrono::Utc::now(),
+        )
+        .await?;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "elevation.approve".to_string(),
+            resource_type: "role_elevation".to_string(),
+            resource_id: elevation.id,
+            org_id: Some(org.id),
+            project_id: None,
+            details: json!({
+                "user_id": elevation.user_id,
+                "role": elevation.role,
+                "previous_role": elevation.previous_role,
+                "expires_at": elevation.expires_at,
+                "note": elevation.decision_note,
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(Json(elevation))
+}
+
+/// Deny a role elevation
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/organizations/{org_slug}/elevations/{id}/deny",
+    tag = "organizations",
+    operation_id = "role_elevation_deny",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("id" = Uuid, Path, description = "Role elevation ID"),
+    ),
+    request_body = ElevationDecision,
+    responses(
+        (status = 200, description = "Elevation denied", body = RoleElevation),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Role elevation not found", body = crate::openapi::ErrorResponse),
+        (status = 409, description = "Role elevation is no longer pending", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "Role elevations are not enabled", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.elevations.deny", skip(state, admin_auth, authz, input), fields(%org_slug, %id))]
+pub async fn deny(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path((org_slug, id)): Path<(String, Uuid)>,
+    Valid(Json(input)): Valid<Json<ElevationDecision>>,
+) -> Result<Json<RoleElevation>, AdminError> {
+    get_config(&state)?;
+    let services = get_services(&state)?;
+    let org = authorize_org(services, &authz, &org_slug, "update").await?;
+    let actor = AuditActor::from(&admin_auth);
+
+    let elevation = get_elevation(services, &org, id).await?;
+    ensure_pending(&elevation)?;
+    let elevation = services
+        .elevations
+        .deny(elevation.id, actor.actor_id, input.note.as_deref())
+        .await?;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "elevation.deny".to_string(),
+            resource_type: "role_elevation".to_string(),
+            resource_id: elevation.id,
+            org_id: Some(org.id),
+            project_id: None,
+            details: json!({
+                "user_id": elevation.user_id,
+                "role": elevation.role,
+                "note": elevation.decision_note,
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(Json(elevation))
+}
+
+/// Revoke a role elevation
+///
+/// Withdraws a pending request, or ends an active elevation early and
+/// restores the member's previous role. Members can revoke their own
+/// elevations; revoking someone else's requires permission to update the
+/// organization.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/organizations/{org_slug}/elevations/{id}/revoke",
+    tag = "organizations",
+    operation_id = "role_elevation_revoke",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("id" = Uuid, Path, description = "Role elevation ID"),
+    ),
+    responses(
+        (status = 200, description = "Elevation revoked", body = RoleElevation),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Role elevation not found", body = crate::openapi::ErrorResponse),
+        (status = 409, description = "Role elevation has already ended", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "Role elevations are not enabled", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.elevations.revoke", skip(state, admin_auth, authz), fields(%org_slug, %id))]
+pub async fn revoke(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path((org_slug, id)): Path<(String, Uuid)>,
+) -> Result<Json<RoleElevation>, AdminError> {
+    get_config(&state)?;
+    let services = get_services(&state)?;
+    let org = authorize_org(services, &authz, &org_slug, "read").await?;
+    let actor = AuditActor::from(&admin_auth);
+
+    let elevation = get_elevation(services, &org, id).await?;
+    if admin_auth.identity.user_id != Some(elevation.user_id) {
+        require_org(&authz, &org, "update")?;
+    }
+    let was_active = elevation.status == ElevationStatus::Active;
+    let elevation = services
+        .elevations
+        .revoke(&elevation, actor.actor_id)
+        .await?;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "elevation.revoke".to_string(),
+            resource_type: "role_elevation".to_string(),
+            resource_id: elevation.id,
+            org_id: Some(org.id),
+            project_id: None,
+            details: json!({
+                "user_id": elevation.user_id,
+                "role": elevation.role,
+                "was_active": was_active,
+                "restored_role": was_active.then_some(&elevation.previous_role),
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(Json(elevation))
+}
@@ -0,0 +1,150 @@
+//! Admin API endpoint for the email send log.
+//!
+//! Every notification email the gateway tries to send (invitations, spend
+//! alerts, access review reminders and DLQ failures) is recorded with its
+//! recipients, subject and outcome, for troubleshooting delivery. Bodies
+//! aren't stored. Entries are pruned by `[retention.periods] email_log_days`.
+
+use axum::{
+    Extension, Json,
+    extract::{Query, State},
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{error::AdminError, organizations::ListQuery};
+use crate::{
+    AppState,
+    db::DbPool,
+    middleware::AuthzContext,
+    models::{EmailKind, EmailLogEntry, EmailLogFilter, EmailSendStatus},
+    openapi::PaginationMeta,
+    services::Services,
+};
+
+/// Query parameters for listing the email send log
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema, utoipa::IntoParams))]
+pub struct EmailLogQuery {
+    /// Only entries sent for this organization
+    pub org_slug: Option<String>,
+    /// Only entries of this kind
+    pub kind: Option<EmailKind>,
+    /// Only entries with this outcome
+    pub status: Option<EmailSendStatus>,
+    /// Maximum number of results to return
+    pub limit: Option<i64>,
+    /// Cursor for keyset pagination. Encoded as base64 string.
+    pub cursor: Option<String>,
+    /// Pagination direction: "forward" (default) or "backward".
+    pub direction: Option<String>,
+}
+
+/// Paginated list of email send log entries
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct EmailLogListResponse {
+    /// Send attempts, newest first
+    pub data: Vec<EmailLogEntry>,
+    /// Pagination metadata
+    pub pagination: PaginationMeta,
+}
+
+fn get_services(state: &AppState) -> Result<&Services, AdminError> {
+    state.services.as_ref().ok_or(AdminError::ServicesRequired)
+}
+
+fn get_db(state: &AppState) -> Result<&DbPool, AdminError> {
+    state.db.as_deref().ok_or(AdminError::DatabaseRequired)
+}
+
+/// List email send attempts
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/email-log",
+    tag = "audit-logs",
+    operation_id = "email_log_list",
+    params(EmailLogQuery),
+    responses(
+        (status = 200, description = "Email send attempts", body = EmailLogListResponse),
+        (status = 400, description = "Invalid cursor or direction", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.email_log.list", skip(state, authz, query))]
+pub async fn list(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Query(query): Query<EmailLogQuery>,
+) -> Result<Json<EmailLogListResponse>, AdminError> {
+    let services = get_services(&state)?;
+    let db = get_db(&state)?;
+
+    let mut org_id = match query.org_slug.as_deref() {
+        Some(slug) => Some(
+            services
+                .organizations
+                .get_by_slug(slug)
+                .await?
+                .ok_or_else(|| AdminError::NotFound(format!("Organization '{}' not found", slug)))?
+                .id,
+        ),
+        None => None,
+    };
+
+    // Members only see their own organization's emails, as with the audit
+    // log. Entries without an organization (DLQ failures) need a subject
+    // with no membership.
+    if let Some(membership) = authz.subject.org_ids.first() {
+        let scoped: Uuid = membership.parse().map_err(|_| {
+            AdminError::Internal(
+                "email_log:list authz subject has a non-UUID org membership".to_string(),
+            )
+        })?;
+        match org_id {
+            Some(requested) if requested != scoped => {
+                return Err(AdminError::Forbidden(
+                    "email_log:list scoped outside your organization".to_string(),
+                ));
+            }
+            _ => org_id = Some(scoped),
+        }
+    }
+
+    let org_scope = org_id.map(|id| id.to_string());
+    authz.require("email_log", "list", None, org_scope.as_deref(), None, None)?;
+
+    let limit = query.limit.unwrap_or(100);
+    let params = ListQuery {
+        limit: query.limit,
+        cursor: query.cursor,
+        direction: query.direction,
+        include_deleted: None,
+    }
+    .try_into_with_cursor()?;
+
+    let result = db
+        .email_log()
+        .list(
+            EmailLogFilter {
+                org_id,
+                kind: query.kind,
+                status: query.status,
+            },
+            params,
+        )
+        .await?;
+
+    let pagination = PaginationMeta::with_cursors(
+        limit,
+        result.has_more,
+        result.cursors.next.map(|c| c.encode()),
+        result.cursors.prev.map(|c| c.encode()),
+    );
+
+    Ok(Json(EmailLogListResponse {
+        data: result.items,
+        pagination,
+    }))
+}
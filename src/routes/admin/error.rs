@@ -8,8 +8,17 @@ use uuid::Uuid;
 #[cfg(feature = "sso")]
 use crate::services::{DomainVerificationError, OrgScimConfigError, OrgSsoConfigError};
 use crate::{
-    auth::Identity, authz::AuthzError, db::DbError, middleware::AdminAuth, models::AuditActorType,
-    observability::metrics, openapi::ErrorResponse, services::OrgRbacPolicyError,
+    auth::Identity,
+    authz::AuthzError,
+    db::DbError,
+    middleware::AdminAuth,
+    models::AuditActorType,
+    observability::metrics,
+    openapi::ErrorResponse,
+    services::{
+        BundleError, EntitlementError, OrgRbacPolicyError, OrgRequestPolicyError, StepUpError,
+        UsageImportError,
+    },
 };
 
 /// Audit actor information extracted from admin authentication.
@@ -169,6 +178,61 @@ impl From<OrgRbacPolicyError> for AdminError {
     }
 }
 
+impl From<OrgRequestPolicyError> for AdminError {
+    fn from(err: OrgRequestPolicyError) -> Self {
+        match err {
+            OrgRequestPolicyError::NotFound => {
+                AdminError::NotFound("Request policy not found".to_string())
+            }
+            OrgRequestPolicyError::InvalidCondition(msg)
+            | OrgRequestPolicyError::InvalidModifications(msg) => AdminError::Validation(msg),
+            OrgRequestPolicyError::Database(db_err) => AdminError::Database(db_err),
+        }
+    }
+}
+
+impl From<BundleError> for AdminError {
+    fn from(err: BundleError) -> Self {
+        match err {
+            BundleError::Database(db_err) => db_err.into(),
+            BundleError::OrganizationNotFound(_) => AdminError::NotFound(err.to_string()),
+            BundleError::Invalid(_) => AdminError::Validation(err.to_string()),
+        }
+    }
+}
+
+impl From<EntitlementError> for AdminError {
+    fn from(err: EntitlementError) -> Self {
+        match err {
+            EntitlementError::Database(db_err) => db_err.into(),
+            EntitlementError::NotEntitled(msg) => AdminError::Forbidden(msg),
+        }
+    }
+}
+
+impl From<StepUpError> for AdminError {
+    fn from(err: StepUpError) -> Self {
+        match err {
+            StepUpError::NotEnrolled => AdminError::BadRequest(err.to_string()),
+            StepUpError::AlreadyEnrolled => AdminError::Conflict(err.to_string()),
+            StepUpError::InvalidCode => AdminError::Validation(err.to_string()),
+            StepUpError::Database(db_err) => AdminError::Database(db_err),
+        }
+    }
+}
+
+impl From<UsageImportError> for AdminError {
+    fn from(err: UsageImportError) -> Self {
+        match err {
+            UsageImportError::Database(db_err) => db_err.into(),
+            UsageImportError::SourceNotConfigured(_) => AdminError::NotConfigured(err.to_string()),
+            // Surface the provider's response so a bad admin key is visible
+            UsageImportError::Fetch(_) => AdminError::BadRequest(err.to_string()),
+            UsageImportError::Invalid(_) => AdminError::Validation(err.to_string()),
+        }
+    }
+}
+
 impl IntoResponse for AdminError {
     fn into_response(self) -> Response {
         // Handle RateLimited specially to add Retry-After header
@@ -0,0 +1,158 @@
+//! Admin API endpoints for the federation hub: satellites that have reported
+//! in, and usage aggregated across them.
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+use super::error::AdminError;
+use crate::{
+    AppState,
+    middleware::AuthzContext,
+    models::{FederatedGateway, FederatedUsageSummary},
+    services::FederationService,
+};
+
+/// Query parameters for the federated usage summary
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema, utoipa::IntoParams))]
+pub struct FederatedUsageQuery {
+    /// Start date (YYYY-MM-DD). Defaults to 30 days before `end_date`.
+    pub start_date: Option<NaiveDate>,
+    /// End date (YYYY-MM-DD), inclusive. Defaults to today (UTC).
+    pub end_date: Option<NaiveDate>,
+    /// Only include usage from this satellite
+    pub gateway_id: Option<String>,
+}
+
+fn get_service(state: &AppState) -> Result<&FederationService, AdminError> {
+    state
+        .services
+        .as_ref()
+        .map(|s| &s.federation)
+        .ok_or(AdminError::ServicesRequired)
+}
+
+/// List federated gateways
+///
+/// Returns every satellite that has reported to this hub, with provider
+/// health from its latest report.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/federation/gateways",
+    tag = "federation",
+    operation_id = "federation_gateway_list",
+    responses(
+        (status = 200, description = "Federated gateways", body = Vec<FederatedGateway>),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn list_gateways(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+) -> Result<Json<Vec<FederatedGateway>>, AdminError> {
+    authz.require("federation", "list", None, None, None, None)?;
+    let service = get_service(&state)?;
+    Ok(Json(service.list_gateways().await?))
+}
+
+/// Get a federated gateway
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/federation/gateways/{id}",
+    tag = "federation",
+    operation_id = "federation_gateway_get",
+    params(("id" = String, Path, description = "Satellite gateway ID")),
+    responses(
+        (status = 200, description = "Federated gateway found", body = FederatedGateway),
+        (status = 404, description = "Gateway has not reported", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn get_gateway(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(id): Path<String>,
+) -> Result<Json<FederatedGateway>, AdminError> {
+    authz.require("federation", "read", Some(&id), None, None, None)?;
+    let service = get_service(&state)?;
+    let gateway = service
+        .get_gateway(&id)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("Federated gateway '{id}' not found")))?;
+    Ok(Json(gateway))
+}
+
+/// Delete a federated gateway
+///
+/// Removes the gateway and all usage it reported. A satellite that is still
+/// configured will reappear on its next report.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    delete,
+    path = "/admin/v1/federation/gateways/{id}",
+    tag = "federation",
+    operation_id = "federation_gateway_delete",
+    params(("id" = String, Path, description = "Satellite gateway ID")),
+    responses(
+        (status = 204, description = "Gateway deleted"),
+        (status = 404, description = "Gateway has not reported", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn delete_gateway(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, AdminError> {
+    authz.require("federation", "delete", Some(&id), None, None, None)?;
+    let service = get_service(&state)?;
+    if !service.delete_gateway(&id).await? {
+        return Err(AdminError::NotFound(format!(
+            "Federated gateway '{id}' not found"
+        )));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Get federated usage summary
+///
+/// Aggregates daily usage reported by satellites into totals per gateway,
+/// per model, and per day.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/federation/summary",
+    tag = "federation",
+    operation_id = "federation_usage_summary",
+    params(FederatedUsageQuery),
+    responses(
+        (status = 200, description = "Federated usage summary", body = FederatedUsageSummary),
+        (status = 400, description = "Invalid date range", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn usage_summary(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Query(query): Query<FederatedUsageQuery>,
+) -> Result<Json<FederatedUsageSummary>, AdminError> {
+    authz.require("federation", "read", None, None, None, None)?;
+    let service = get_service(&state)?;
+
+    let end_date = query
+        .end_date
+        .unwrap_or_else(|| chrono::Utc::now().date_naive());
+    let start_date = query
+        .start_date
+        .unwrap_or(end_date - chrono::Duration::days(30));
+    if end_date < start_date {
+        return Err(AdminError::BadRequest(
+            "end_date must be >= start_date".to_string(),
+        ));
+    }
+
+    let summary = service
+        .usage_summary(start_date, end_date, query.gateway_id.as_deref())
+        .await?;
+    Ok(Json(summary))
+}
@@ -0,0 +1,725 @@
+//! GraphQL admin API.
+//!
+//! A read-only query surface over the services layer for tooling that would
+//! otherwise stitch together many REST aggregates. Every resolver runs the
+//! same authorization check as the REST endpoint it mirrors. Relations that
+//! fan out per row (a project's organization, per-organization and
+//! per-project usage) go through dataloaders, so a page of results costs one
+//! query per relation rather than one per row.
+
+use std::{collections::HashMap, sync::Arc};
+
+use async_graphql::{
+    Context, EmptyMutation, EmptySubscription, ErrorExtensions, Object, Schema, SimpleObject,
+    dataloader::{DataLoader, Loader},
+};
+use axum::{Extension, Json, Router, extract::State, routing::post};
+use chrono::{DateTime, NaiveDate, Utc};
+use uuid::Uuid;
+
+use super::AdminError;
+use crate::{
+    AppState,
+    config::GraphqlConfig,
+    db::{DateRange, ListParams},
+    middleware::AuthzContext,
+    models::{ApiKey, Organization, Project, UsageSummary},
+    services::Services,
+};
+
+pub type AdminSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Build the schema with the configured depth and complexity limits.
+pub fn schema(config: &GraphqlConfig) -> AdminSchema {
+    let builder = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .limit_depth(config.max_depth)
+        .limit_complexity(config.max_complexity);
+    if config.introspection {
+        builder.finish()
+    } else {
+        builder.disable_introspection().finish()
+    }
+}
+
+/// `/graphql`, to be nested under `/admin` inside the admin auth layers.
+pub fn routes(config: &GraphqlConfig) -> Router<AppState> {
+    Router::new()
+        .route("/graphql", post(execute))
+        .layer(Extension(schema(config)))
+}
+
+/// Execute a GraphQL query against the admin schema.
+pub async fn execute(
+    State(state): State<AppState>,
+    Extension(schema): Extension<AdminSchema>,
+    Extension(authz): Extension<AuthzContext>,
+    Json(request): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    let mut request = request.data(authz);
+    // Loaders are per request so cached rows never outlive the caller's
+    // authorization checks.
+    if let Some(services) = &state.services {
+        request = request
+            .data(DataLoader::new(
+                OrganizationLoader {
+                    services: services.clone(),
+                },
+                tokio::spawn,
+            ))
+            .data(DataLoader::new(
+                UsageLoader {
+                    services: services.clone(),
+                },
+                tokio::spawn,
+            ));
+    }
+    Json(schema.execute(request.data(state)).await)
+}
+
+/// Convert an error into a GraphQL error with the REST API's error code in
+/// `extensions.code`. Internal details are logged, not returned.
+fn gql_error(err: impl Into<AdminError>) -> async_graphql::Error {
+    let (code, message) = match err.into() {
+        AdminError::NotFound(msg) => ("not_found", msg),
+        AdminError::BadRequest(msg) => ("bad_request", msg),
+        AdminError::Validation(msg) => ("validation_error", msg),
+        AdminError::Forbidden(msg) => ("forbidden", msg),
+        AdminError::DatabaseRequired | AdminError::ServicesRequired => (
+            "feature_not_available",
+            "This query requires database support".to_string(),
+        ),
+        err => {
+            tracing::error!(error = ?err, "GraphQL resolver error");
+            ("internal_error", "An internal error occurred".to_string())
+        }
+    };
+    async_graphql::Error::new(message).extend_with(|_, e| e.set("code", code))
+}
+
+/// Services and authorization context for a resolver.
+fn scope<'a>(ctx: &Context<'a>) -> async_graphql::Result<(&'a Services, &'a AuthzContext)> {
+    let state = ctx.data::<AppState>()?;
+    let services = state
+        .services
+        .as_ref()
+        .ok_or_else(|| gql_error(AdminError::ServicesRequired))?;
+    Ok((services, ctx.data::<AuthzContext>()?))
+}
+
+/// List parameters for a `first` argument, clamped like the REST `limit`.
+fn page(first: i32) -> ListParams {
+    ListParams {
+        limit: Some(first.into()),
+        ..Default::default()
+    }
+    .clamp()
+}
+
+/// Date range from optional arguments, defaulting to today like the REST
+/// usage endpoints.
+fn date_range(
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+) -> async_graphql::Result<DateRange> {
+    let today = Utc::now().date_naive();
+    let range = DateRange {
+        start: start_date.unwrap_or(today),
+        end: end_date.unwrap_or(today),
+    };
+    if range.end < range.start {
+        return Err(gql_error(AdminError::BadRequest(
+            "endDate must be >= startDate".to_string(),
+        )));
+    }
+    Ok(range)
+}
+
+// ==================== Dataloaders ====================
+
+/// Loads organizations by ID, deduplicating a page's parent lookups.
+pub struct OrganizationLoader {
+    services: Services,
+}
+
+impl Loader<Uuid> for OrganizationLoader {
+    type Value = Organization;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Organization>, Self::Error> {
+        let orgs = futures::future::try_join_all(
+            keys.iter()
+                .map(|&id| self.services.organizations.get_by_id(id)),
+        )
+        .await
+        .map_err(gql_error)?;
+        Ok(orgs
+            .into_iter()
+            .flatten()
+            .map(|org| (org.id, org))
+            .collect())
+    }
+}
+
+/// A usage breakdown that covers many entities with one query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UsageKey {
+    /// Every organization's usage
+    Organizations { start: NaiveDate, end: NaiveDate },
+    /// Usage of every project in an organization
+    Projects {
+        org_id: Uuid,
+        start: NaiveDate,
+        end: NaiveDate,
+    },
+}
+
+/// Loads per-entity usage through the by-org and by-project aggregates, so
+/// the `usage` field of every row in a page shares one query.
+pub struct UsageLoader {
+    services: Services,
+}
+
+impl UsageLoader {
+    async fn load_key(&self, key: UsageKey) -> Result<HashMap<Uuid, UsageTotals>, AdminError> {
+        Ok(match key {
+            UsageKey::Organizations { start, end } => self
+                .services
+                .usage
+                .get_by_org_global(DateRange { start, end })
+                .await?
+                .into_iter()
+                .filter_map(|spend| {
+                    Some((
+                        spend.org_id?,
+                        UsageTotals::new(
+                            spend.total_cost_microcents,
+                            spend.input_tokens,
+                            spend.output_tokens,
+                            spend.total_tokens,
+                            spend.request_count,
+                        ),
+                    ))
+                })
+                .collect(),
+            UsageKey::Projects { org_id, start, end } => self
+                .services
+                .usage
+                .get_by_project_by_org(org_id, DateRange { start, end })
+                .await?
+                .into_iter()
+                .filter_map(|spend| {
+                    Some((
+                        spend.project_id?,
+                        UsageTotals::new(
+                            spend.total_cost_microcents,
+                            spend.input_tokens,
+                            spend.output_tokens,
+                            spend.total_tokens,
+                            spend.request_count,
+                        ),
+                    ))
+                })
+                .collect(),
+        })
+    }
+}
+
+impl Loader<UsageKey> for UsageLoader {
+    type Value = Arc<HashMap<Uuid, UsageTotals>>;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[UsageKey]) -> Result<HashMap<UsageKey, Self::Value>, Self::Error> {
+        let loaded = futures::future::try_join_all(keys.iter().map(|&key| async move {
+            Ok::<_, AdminError>((key, Arc::new(self.load_key(key).await?)))
+        }))
+        .await
+        .map_err(gql_error)?;
+        Ok(loaded.into_iter().collect())
+    }
+}
+
+/// Usage for one entity through [`UsageLoader`]; zero when it has none.
+async fn load_usage(
+    ctx: &Context<'_>,
+    key: UsageKey,
+    id: Uuid,
+) -> async_graphql::Result<UsageTotals> {
+    let usage = ctx.data::<DataLoader<UsageLoader>>()?.load_one(key).await?;
+    Ok(usage
+        .and_then(|usage| usage.get(&id).cloned())
+        .unwrap_or_default())
+}
+
+// ==================== Types ====================
+
+/// Usage totals over a date range.
+#[derive(Debug, Clone, Default, SimpleObject)]
+#[graphql(name = "Usage")]
+pub struct UsageTotals {
+    /// Total cost in dollars
+    pub total_cost: f64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub total_tokens: i64,
+    pub request_count: i64,
+}
+
+impl UsageTotals {
+    fn new(
+        total_cost_microcents: i64,
+        input_tokens: i64,
+        output_tokens: i64,
+        total_tokens: i64,
+        request_count: i64,
+    ) -> Self {
+        Self {
+            total_cost: total_cost_microcents as f64 / 1_000_000.0,
+            input_tokens,
+            output_tokens,
+            total_tokens,
+            request_count,
+        }
+    }
+}
+
+impl From<UsageSummary> for UsageTotals {
+    fn from(summary: UsageSummary) -> Self {
+        Self::new(
+            summary.total_cost_microcents,
+            summary.input_tokens,
+            summary.output_tokens,
+            summary.total_tokens,
+            summary.request_count,
+        )
+    }
+}
+
+/// A configured (static) provider and its live routing state.
+#[derive(Debug, Clone, SimpleObject)]
+#[graphql(name = "Provider")]
+pub struct ProviderNode {
+    pub name: String,
+    /// Provider type, e.g. `openai` or `bedrock`
+    pub provider_type: String,
+    /// Circuit breaker state (`closed`, `open` or `half_open`); `null` until
+    /// the provider has served a request
+    pub circuit_state: Option<String>,
+    /// Health check status (`healthy`, `unhealthy` or `unknown`); `null`
+    /// when health checks are disabled
+    pub health: Option<String>,
+}
+
+pub struct OrganizationNode(Organization);
+
+#[Object(name = "Organization")]
+impl OrganizationNode {
+    async fn id(&self) -> Uuid {
+        self.0.id
+    }
+
+    async fn slug(&self) -> &str {
+        &self.0.slug
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.0.updated_at
+    }
+
+    /// The organization's projects, oldest first
+    #[graphql(complexity = "first.max(1) as usize * child_complexity")]
+    async fn projects(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 20)] first: i32,
+    ) -> async_graphql::Result<Vec<ProjectNode>> {
+        let (services, authz) = scope(ctx)?;
+        authz
+            .require(
+                "project",
+                "list",
+                None,
+                Some(&self.0.id.to_string()),
+                None,
+                None,
+            )
+            .map_err(gql_error)?;
+        let projects = services
+            .projects
+            .list_by_org(self.0.id, page(first))
+            .await
+            .map_err(gql_error)?;
+        Ok(projects.items.into_iter().map(ProjectNode).collect())
+    }
+
+    /// API keys owned by the organization itself
+    #[graphql(complexity = "first.max(1) as usize * child_complexity")]
+    async fn api_keys(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 20)] first: i32,
+    ) -> async_graphql::Result<Vec<ApiKeyNode>> {
+        let (services, authz) = scope(ctx)?;
+        authz
+            .require(
+                "api_key",
+                "list",
+                None,
+                Some(&self.0.id.to_string()),
+                None,
+                None,
+            )
+            .map_err(gql_error)?;
+        let keys = services
+            .api_keys
+            .list_by_org(self.0.id, page(first))
+            .await
+            .map_err(gql_error)?;
+        Ok(keys.items.into_iter().map(ApiKeyNode).collect())
+    }
+
+    /// Usage between `startDate` and `endDate` (inclusive, default today)
+    async fn usage(
+        &self,
+        ctx: &Context<'_>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> async_graphql::Result<UsageTotals> {
+        let (_, authz) = scope(ctx)?;
+        authz
+            .require(
+                "usage",
+                "read",
+                None,
+                Some(&self.0.id.to_string()),
+                None,
+                None,
+            )
+            .map_err(gql_error)?;
+        let range = date_range(start_date, end_date)?;
+        let key = UsageKey::Organizations {
+            start: range.start,
+            end: range.end,
+        };
+        load_usage(ctx, key, self.0.id).await
+    }
+}
+
+pub struct ProjectNode(Project);
+
+#[Object(name = "Project")]
+impl ProjectNode {
+    async fn id(&self) -> Uuid {
+        self.0.id
+    }
+
+    async fn org_id(&self) -> Uuid {
+        self.0.org_id
+    }
+
+    async fn team_id(&self) -> Option<Uuid> {
+        self.0.team_id
+    }
+
+    async fn slug(&self) -> &str {
+        &self.0.slug
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.0.updated_at
+    }
+
+    /// The organization the project belongs to
+    async fn organization(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Option<OrganizationNode>> {
+        let (_, authz) = scope(ctx)?;
+        let org_id = self.0.org_id.to_string();
+        authz
+            .require(
+                "organization",
+                "read",
+                Some(&org_id),
+                Some(&org_id),
+                None,
+                None,
+            )
+            .map_err(gql_error)?;
+        let org = ctx
+            .data::<DataLoader<OrganizationLoader>>()?
+            .load_one(self.0.org_id)
+            .await?;
+        Ok(org.map(OrganizationNode))
+    }
+
+    /// API keys owned by the project
+    #[graphql(complexity = "first.max(1) as usize * child_complexity")]
+    async fn api_keys(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 20)] first: i32,
+    ) -> async_graphql::Result<Vec<ApiKeyNode>> {
+        let (services, authz) = scope(ctx)?;
+        authz
+            .require(
+                "api_key",
+                "list",
+                None,
+                Some(&self.0.org_id.to_string()),
+                None,
+                Some(&self.0.id.to_string()),
+            )
+            .map_err(gql_error)?;
+        let keys = services
+            .api_keys
+            .list_by_project(self.0.id, page(first))
+            .await
+            .map_err(gql_error)?;
+        Ok(keys.items.into_iter().map(ApiKeyNode).collect())
+    }
+
+    /// Usage between `startDate` and `endDate` (inclusive, default today)
+    async fn usage(
+        &self,
+        ctx: &Context<'_>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> async_graphql::Result<UsageTotals> {
+        let (_, authz) = scope(ctx)?;
+        authz
+            .require(
+                "usage",
+                "read",
+                None,
+                Some(&self.0.org_id.to_string()),
+                None,
+                None,
+            )
+            .map_err(gql_error)?;
+        let range = date_range(start_date, end_date)?;
+        let key = UsageKey::Projects {
+            org_id: self.0.org_id,
+            start: range.start,
+            end: range.end,
+        };
+        load_usage(ctx, key, self.0.id).await
+    }
+}
+
+pub struct ApiKeyNode(ApiKey);
+
+#[Object(name = "ApiKey")]
+impl ApiKeyNode {
+    async fn id(&self) -> Uuid {
+        self.0.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    /// First characters of the key, for identification
+    async fn key_prefix(&self) -> &str {
+        &self.0.key_prefix
+    }
+
+    async fn scopes(&self) -> Option<&[String]> {
+        self.0.scopes.as_deref()
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.0.expires_at
+    }
+
+    async fn revoked_at(&self) -> Option<DateTime<Utc>> {
+        self.0.revoked_at
+    }
+
+    async fn last_used_at(&self) -> Option<DateTime<Utc>> {
+        self.0.last_used_at
+    }
+}
+
+// ==================== Query Root ====================
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Organizations, oldest first
+    #[graphql(complexity = "first.max(1) as usize * child_complexity")]
+    async fn organizations(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 20)] first: i32,
+    ) -> async_graphql::Result<Vec<OrganizationNode>> {
+        let (services, authz) = scope(ctx)?;
+        authz
+            .require("organization", "list", None, None, None, None)
+            .map_err(gql_error)?;
+        let orgs = services
+            .organizations
+            .list(page(first))
+            .await
+            .map_err(gql_error)?;
+        Ok(orgs.items.into_iter().map(OrganizationNode).collect())
+    }
+
+    /// An organization by slug
+    async fn organization(
+        &self,
+        ctx: &Context<'_>,
+        slug: String,
+    ) -> async_graphql::Result<Option<OrganizationNode>> {
+        let (services, authz) = scope(ctx)?;
+        let Some(org) = services
+            .organizations
+            .get_by_slug(&slug)
+            .await
+            .map_err(gql_error)?
+        else {
+            return Ok(None);
+        };
+        let org_id = org.id.to_string();
+        authz
+            .require(
+                "organization",
+                "read",
+                Some(&org_id),
+                Some(&org_id),
+                None,
+                None,
+            )
+            .map_err(gql_error)?;
+        Ok(Some(OrganizationNode(org)))
+    }
+
+    /// Providers from the configuration file
+    async fn providers(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<ProviderNode>> {
+        let authz = ctx.data::<AuthzContext>()?;
+        authz
+            .require("provider", "list", None, None, None, None)
+            .map_err(gql_error)?;
+        let state = ctx.data::<AppState>()?;
+        let as_str = |value: serde_json::Value| value.as_str().map(String::from);
+        Ok(state
+            .config
+            .providers
+            .iter()
+            .map(|(name, config)| ProviderNode {
+                name: name.to_string(),
+                provider_type: config.provider_type_name().to_string(),
+                circuit_state: state
+                    .circuit_breakers
+                    .status_for(name)
+                    .and_then(|status| serde_json::to_value(status.state).ok())
+                    .and_then(as_str),
+                health: state
+                    .provider_health
+                    .get(name)
+                    .and_then(|health| serde_json::to_value(health.status).ok())
+                    .and_then(as_str),
+            })
+            .collect())
+    }
+
+    /// Usage across all organizations between `startDate` and `endDate`
+    /// (inclusive, default today)
+    async fn usage(
+        &self,
+        ctx: &Context<'_>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> async_graphql::Result<UsageTotals> {
+        let (services, authz) = scope(ctx)?;
+        authz
+            .require("usage", "read", None, None, None, None)
+            .map_err(gql_error)?;
+        let range = date_range(start_date, end_date)?;
+        let summary = services
+            .usage
+            .get_summary_global(range)
+            .await
+            .map_err(gql_error)?;
+        Ok(summary.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn query_errors(config: GraphqlConfig, query: &str) -> Vec<String> {
+        schema(&config)
+            .execute(query)
+            .await
+            .errors
+            .into_iter()
+            .map(|e| e.message)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_depth_limit() {
+        let config = GraphqlConfig {
+            max_depth: 3,
+            ..Default::default()
+        };
+        let query = "{ organizations { projects { organization { id } } } }";
+        let errors = query_errors(config, query).await;
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("too deep"), "{errors:?}");
+    }
+
+    #[tokio::test]
+    async fn test_complexity_scales_with_page_size() {
+        let config = GraphqlConfig {
+            max_complexity: 100,
+            ..Default::default()
+        };
+        let query = "{ organizations(first: 50) { id projects(first: 50) { id name } } }";
+        let errors = query_errors(config.clone(), query).await;
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("too complex"), "{errors:?}");
+
+        // Within the limits resolvers run, failing here only for lack of state
+        let errors = query_errors(config, "{ organizations(first: 5) { id } }").await;
+        assert!(!errors.iter().any(|e| e.contains("too complex")));
+    }
+
+    #[tokio::test]
+    async fn test_introspection_can_be_disabled() {
+        let query = "{ __schema { queryType { name } } }";
+        assert!(
+            query_errors(GraphqlConfig::default(), query)
+                .await
+                .is_empty()
+        );
+
+        let config = GraphqlConfig {
+            introspection: false,
+            ..Default::default()
+        };
+        assert!(!query_errors(config, query).await.is_empty());
+    }
+}
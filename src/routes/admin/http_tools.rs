@@ -0,0 +1,332 @@
+//! Admin API endpoints for an organization's custom HTTP tools.
+//!
+//! A registered tool works like a `[[features.server_tools.http_tools]]`
+//! entry, scoped to one organization: requests made in the organization opt
+//! in by declaring a function tool with the tool's name, and the gateway
+//! POSTs the model's arguments to the tool's URL.
+
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use axum_valid::Valid;
+use serde::Serialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use super::{AuditActor, error::AdminError};
+use crate::{
+    AppState,
+    config::{is_reserved_server_tool_name, is_valid_http_tool_name},
+    middleware::{AdminAuth, AuthzContext, ClientInfo},
+    models::{CreateAuditLog, CreateHttpTool, HttpTool, Organization, UpdateHttpTool},
+    services::Services,
+    validation::{UrlValidationOptions, validate_base_url_opts},
+};
+
+fn get_services(state: &AppState) -> Result<&Services, AdminError> {
+    state.services.as_ref().ok_or(AdminError::ServicesRequired)
+}
+
+async fn get_org(services: &Services, org_slug: &str) -> Result<Organization, AdminError> {
+    services
+        .organizations
+        .get_by_slug(org_slug)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("Organization '{}' not found", org_slug)))
+}
+
+/// Load an HTTP tool of `org`. Other organizations' tools are reported as
+/// missing.
+async fn get_tool(
+    services: &Services,
+    org: &Organization,
+    tool_id: Uuid,
+) -> Result<HttpTool, AdminError> {
+    services
+        .http_tools
+        .get_by_id(tool_id)
+        .await?
+        .filter(|tool| tool.org_id == org.id)
+        .ok_or_else(|| AdminError::NotFound(format!("HTTP tool '{tool_id}' not found")))
+}
+
+fn validate_name(state: &AppState, name: &str) -> Result<(), AdminError> {
+    if !is_valid_http_tool_name(name) {
+        return Err(AdminError::Validation(format!(
+            "Name '{name}' must match [A-Za-z0-9_-]{{1,64}}"
+        )));
+    }
+    if is_reserved_server_tool_name(name) {
+        return Err(AdminError::Validation(format!(
+            "Name '{name}' is reserved for a built-in tool"
+        )));
+    }
+    if state.config.features.server_tools.http_tool(name).is_some() {
+        return Err(AdminError::Validation(format!(
+            "Name '{name}' is already used by a configured HTTP tool"
+        )));
+    }
+    Ok(())
+}
+
+/// Unlike configured tools, registered URLs come from organization admins,
+/// so they're held to the same SSRF rules as `web_fetch`.
+fn validate_url(state: &AppState, url: &str) -> Result<(), AdminError> {
+    let opts = UrlValidationOptions {
+        allow_loopback: state.config.server.allow_loopback_urls,
+        allow_private: state.config.server.allow_private_urls,
+    };
+    validate_base_url_opts(url, opts)
+        .map(|_| ())
+        .map_err(|e| AdminError::Validation(format!("Invalid URL: {e}")))
+}
+
+fn validate_parameters(parameters: Option<&serde_json::Value>) -> Result<(), AdminError> {
+    if parameters.is_some_and(|p| !p.is_object()) {
+        return Err(AdminError::Validation(
+            "parameters must be a JSON Schema object".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+async fn audit(
+    services: &Services,
+    admin_auth: &AdminAuth,
+    client_info: ClientInfo,
+    action: &str,
+    tool: &HttpTool,
+) {
+    let actor = AuditActor::from(admin_auth);
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: format!("http_tool.{action}"),
+            resource_type: "http_tool".to_string(),
+            resource_id: tool.id,
+            org_id: Some(tool.org_id),
+            project_id: None,
+            details: json!({ "name": tool.name, "url": tool.url }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+}
+
+/// List of an organization's HTTP tools, ordered by name
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct HttpToolListResponse {
+    pub data: Vec<HttpTool>,
+}
+
+/// List an organization's HTTP tools
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/organizations/{org_slug}/http-tools",
+    tag = "http-tools",
+    operation_id = "http_tool_list",
+    params(("org_slug" = String, Path, description = "Organization slug")),
+    responses(
+        (status = 200, description = "The organization's HTTP tools", body = HttpToolListResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn list(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(org_slug): Path<String>,
+) -> Result<Json<HttpToolListResponse>, AdminError> {
+    let services = get_services(&state)?;
+    let org = get_org(services, &org_slug).await?;
+    authz.require(
+        "http_tool",
+        "list",
+        None,
+        Some(&org.id.to_string()),
+        None,
+        None,
+    )?;
+    let data = services.http_tools.list_by_org(org.id).await?;
+    Ok(Json(HttpToolListResponse { data }))
+}
+
+/// Register an HTTP tool
+///
+/// The name can't shadow a built-in tool or a configured HTTP tool, and the
+/// URL must pass the gateway's SSRF checks. Header values are write-only.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/organizations/{org_slug}/http-tools",
+    tag = "http-tools",
+    operation_id = "http_tool_create",
+    params(("org_slug" = String, Path, description = "Organization slug")),
+    request_body = CreateHttpTool,
+    responses(
+        (status = 201, description = "HTTP tool registered", body = HttpTool),
+        (status = 400, description = "Invalid name, URL or parameters", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found", body = crate::openapi::ErrorResponse),
+        (status = 409, description = "HTTP tool with same name already exists", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn create(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path(org_slug): Path<String>,
+    Valid(Json(input)): Valid<Json<CreateHttpTool>>,
+) -> Result<(StatusCode, Json<HttpTool>), AdminError> {
+    let services = get_services(&state)?;
+    let org = get_org(services, &org_slug).await?;
+    authz.require(
+        "http_tool",
+        "create",
+        None,
+        Some(&org.id.to_string()),
+        None,
+        None,
+    )?;
+    validate_name(&state, &input.name)?;
+    validate_url(&state, &input.url)?;
+    validate_parameters(input.parameters.as_ref())?;
+
+    let tool = services.http_tools.create(org.id, input).await?;
+
+    audit(services, &admin_auth, client_info, "create", &tool).await;
+    Ok((StatusCode::CREATED, Json(tool)))
+}
+
+/// Get an HTTP tool
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/organizations/{org_slug}/http-tools/{tool_id}",
+    tag = "http-tools",
+    operation_id = "http_tool_get",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("tool_id" = Uuid, Path, description = "HTTP tool ID"),
+    ),
+    responses(
+        (status = 200, description = "The HTTP tool", body = HttpTool),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "HTTP tool not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn get(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path((org_slug, tool_id)): Path<(String, Uuid)>,
+) -> Result<Json<HttpTool>, AdminError> {
+    let services = get_services(&state)?;
+    let org = get_org(services, &org_slug).await?;
+    authz.require(
+        "http_tool",
+        "read",
+        Some(&tool_id.to_string()),
+        Some(&org.id.to_string()),
+        None,
+        None,
+    )?;
+    Ok(Json(get_tool(services, &org, tool_id).await?))
+}
+
+/// Update an HTTP tool
+///
+/// Omitted fields are left unchanged; `headers` replaces the whole set.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    patch,
+    path = "/admin/v1/organizations/{org_slug}/http-tools/{tool_id}",
+    tag = "http-tools",
+    operation_id = "http_tool_update",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("tool_id" = Uuid, Path, description = "HTTP tool ID"),
+    ),
+    request_body = UpdateHttpTool,
+    responses(
+        (status = 200, description = "HTTP tool updated", body = HttpTool),
+        (status = 400, description = "Invalid name, URL or parameters", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "HTTP tool not found", body = crate::openapi::ErrorResponse),
+        (status = 409, description = "HTTP tool with same name already exists", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn update(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path((org_slug, tool_id)): Path<(String, Uuid)>,
+    Valid(Json(input)): Valid<Json<UpdateHttpTool>>,
+) -> Result<Json<HttpTool>, AdminError> {
+    let services = get_services(&state)?;
+    let org = get_org(services, &org_slug).await?;
+    authz.require(
+        "http_tool",
+        "update",
+        Some(&tool_id.to_string()),
+        Some(&org.id.to_string()),
+        None,
+        None,
+    )?;
+    get_tool(services, &org, tool_id).await?;
+    if let Some(name) = &input.name {
+        validate_name(&state, name)?;
+    }
+    if let Some(url) = &input.url {
+        validate_url(&state, url)?;
+    }
+    validate_parameters(input.parameters.as_ref())?;
+
+    let tool = services.http_tools.update(tool_id, input).await?;
+
+    audit(services, &admin_auth, client_info, "update", &tool).await;
+    Ok(Json(tool))
+}
+
+/// Delete an HTTP tool
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    delete,
+    path = "/admin/v1/organizations/{org_slug}/http-tools/{tool_id}",
+    tag = "http-tools",
+    operation_id = "http_tool_delete",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("tool_id" = Uuid, Path, description = "HTTP tool ID"),
+    ),
+    responses(
+        (status = 204, description = "HTTP tool deleted"),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "HTTP tool not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn delete(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path((org_slug, tool_id)): Path<(String, Uuid)>,
+) -> Result<StatusCode, AdminError> {
+    let services = get_services(&state)?;
+    let org = get_org(services, &org_slug).await?;
+    authz.require(
+        "http_tool",
+        "delete",
+        Some(&tool_id.to_string()),
+        Some(&org.id.to_string()),
+        None,
+        None,
+    )?;
+    let tool = get_tool(services, &org, tool_id).await?;
+    services.http_tools.delete(tool_id).await?;
+
+    audit(services, &admin_auth, client_info, "delete", &tool).await;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,461 @@
+//! Admin API endpoints for an organization's invitations.
+//!
+//! An invitation lets an email address join the organization, and
+//! optionally one of its teams, by opening the invite link and signing in
+//! through SSO. The link is returned once by the create and resend
+//! endpoints, and sent through `[features.invitations] delivery` when one is
+//! configured.
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use axum_valid::Valid;
+use serde::Serialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use super::{AuditActor, error::AdminError, organizations::ListQuery};
+use crate::{
+    AppState,
+    config::InvitationsConfig,
+    middleware::{AdminAuth, AuthzContext, ClientInfo},
+    models::{CreateAuditLog, CreateInvitation, Invitation, InvitationStatus, Organization, Team},
+    openapi::PaginationMeta,
+    services::{Services, invitations},
+};
+
+/// Paginated list of invitations
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct InvitationListResponse {
+    /// List of invitations
+    pub data: Vec<Invitation>,
+    /// Pagination metadata
+    pub pagination: PaginationMeta,
+}
+
+/// An invitation with its invite link
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct InvitationWithLink {
+    #[serde(flatten)]
+    pub invitation: Invitation,
+    /// Invite link. Only returned here; it cannot be retrieved later, but
+    /// resending issues a new one.
+    pub invite_url: String,
+    /// Whether the link was sent through the configured delivery channel
+    pub delivered: bool,
+    /// Why delivery failed, if it did. The invitation is still valid.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivery_error: Option<String>,
+}
+
+fn get_services(state: &AppState) -> Result<&Services, AdminError> {
+    state.services.as_ref().ok_or(AdminError::ServicesRequired)
+}
+
+fn get_config(state: &AppState) -> Result<&InvitationsConfig, AdminError> {
+    let config = &state.config.features.invitations;
+    if !config.enabled {
+        return Err(AdminError::NotConfigured(
+            "Invitations are not enabled".to_string(),
+        ));
+    }
+    Ok(config)
+}
+
+/// Look up an organization and require `action` on it.
+async fn authorize_org(
+    services: &Services,
+    authz: &AuthzContext,
+    org_slug: &str,
+    action: &str,
+) -> Result<Organization, AdminError> {
+    let org = services
+        .organizations
+        .get_by_slug(org_slug)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("Organization '{}' not found", org_slug)))?;
+
+    authz.require(
+        "organization",
+        action,
+        Some(&org.id.to_string()),
+        Some(&org.id.to_string()),
+        None,
+        None,
+    )?;
+    Ok(org)
+}
+
+/// Load an invitation of `org`. Other organizations' invitations are
+/// reported as not found.
+async fn get_invitation(
+    services: &Services,
+    org: &Organization,
+    id: Uuid,
+) -> Result<Invitation, AdminError> {
+    services
+        .invitations
+        .get_by_id(id)
+        .await?
+        .filter(|i| i.org_id == org.id)
+        .ok_or_else(|| AdminError::NotFound(format!("Invitation '{}' not found", id)))
+}
+
+/// Build the invite link and send it through the configured channel.
+/// Delivery failures are reported in the response rather than failing the
+/// request, since the link can still be shared by hand.
+async fn deliver(
+    state: &AppState,
+    services: &Services,
+    config: &InvitationsConfig,
+    org: &Organization,
+    invitation: Invitation,
+    token: &str,
+) -> Result<InvitationWithLink, AdminError> {
+    let invite_url = invitations::invite_url(config, token);
+    let team = match invitation.team_id {
+        Some(team_id) => services.teams.get_by_id(team_id).await?,
+        None => None,
+    };
+
+    let (delivered, delivery_error) = match invitations::deliver(
+        &state.http_client,
+        config,
+        state.email_notifier.as_deref(),
+        &invitation,
+        org,
+        team.as_ref(),
+        &invite_url,
+    )
+    .await
+    {
+        Ok(delivered) => (delivered, None),
+        Err(e) => {
+            tracing::warn!(
+                invitation_id = %invitation.id,
+                error = %e,
+                "Failed to deliver invitation"
+            );
+            (false, Some(e))
+        }
+    };
+
+    let invitation = if delivered {
+        services.invitations.mark_sent(invitation.id).await?;
+        get_invitation(services, org, invitation.id).await?
+    } else {
+        invitation
+    };
+
+    Ok(InvitationWithLink {
+        invitation,
+        invite_url,
+        delivered,
+        delivery_error,
+    })
+}
+
+/// Invite an email address to an organization
+///
+/// Creates a pending invitation that expires after
+/// `[features.invitations] ttl_secs` and sends the invite link through the
+/// configured delivery channel. With `team_slug`, the invitee joins that team
+/// with `role` and the organization as a `member`.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/organizations/{org_slug}/invitations",
+    tag = "organizations",
+    operation_id = "invitation_create",
+    params(("org_slug" = String, Path, description = "Organization slug")),
+    request_body = CreateInvitation,
+    responses(
+        (status = 201, description = "Invitation created", body = InvitationWithLink),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization or team not found", body = crate::openapi::ErrorResponse),
+        (status = 409, description = "The address already has a pending invitation", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "Invitations are not enabled", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.invitations.create", skip(state, admin_auth, authz, input), fields(%org_slug))]
+pub async fn create(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path(org_slug): Path<String>,
+    Valid(Json(input)): Valid<Json<CreateInvitation>>,
+) -> Result<(StatusCode, Json<InvitationWithLink>), AdminError> {
+    let config = get_config(&state)?;
+    let services = get_services(&state)?;
+    let org = authorize_org(services, &authz, &org_slug, "update").await?;
+    let actor = AuditActor::from(&admin_auth);
+
+    let team: Option<Team> = match &input.team_slug {
+        Some(team_slug) => Some(
+            services
+                .teams
+                .get_by_slug(org.id, team_slug)
+                .await?
+                .ok_or_else(|| {
+                    AdminError::NotFound(format!(
+                        "Team '{}' not found in organization '{}'",
+                        team_slug, org_slug
+                    ))
+                })?,
+        ),
+        None => None,
+    };
+
+    let (invitation, token) = services
+        .invitations
+        .create(
+            org.id,
+            team.as_ref().map(|t| t.id),
+            &input.email,
+            input.role,
+            actor.actor_id,
+            chrono::Utc::now() + config.ttl(),
+        )
+        .await?;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "invitation.create".to_string(),
+            resource_type: "invitation".to_string(),
+            resource_id: invitation.id,
+            org_id: Some(org.id),
+            project_id: None,
+            details: json!({
+                "email": invitation.email,
+                "role": invitation.role,
+                "team_slug": team.as_ref().map(|t| &t.slug),
+                "expires_at": invitation.expires_at,
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    let response = deliver(&state, services, config, &org, invitation, &token).await?;
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+/// List an organization's invitations
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/organizations/{org_slug}/invitations",
+    tag = "organizations",
+    operation_id = "invitation_list",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ListQuery,
+    ),
+    responses(
+        (status = 200, description = "List of invitations", body = InvitationListResponse),
+        (status = 400, description = "Invalid cursor or direction", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "Invitations are not enabled", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.invitations.list", skip(state, authz, query), fields(%org_slug))]
+pub async fn list(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(org_slug): Path<String>,
+    Query(query): Query<ListQuery>,
+) -> Result<Json<InvitationListResponse>, AdminError> {
+    get_config(&state)?;
+    let services = get_services(&state)?;
+    let org = authorize_org(services, &authz, &org_slug, "read").await?;
+
+    let limit = query.limit.unwrap_or(100);
+    let params = query.try_into_with_cursor()?;
+
+    let result = services.invitations.list_by_org(org.id, params).await?;
+
+    let pagination = PaginationMeta::with_cursors(
+        limit,
+        result.has_more,
+        result.cursors.next.map(|c| c.encode()),
+        result.cursors.prev.map(|c| c.encode()),
+    );
+
+    Ok(Json(InvitationListResponse {
+        data: result.items,
+        pagination,
+    }))
+}
+
+/// Get an invitation
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/organizations/{org_slug}/invitations/{id}",
+    tag = "organizations",
+    operation_id = "invitation_get",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("id" = Uuid, Path, description = "Invitation ID"),
+    ),
+    responses(
+        (status = 200, description = "Invitation", body = Invitation),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Invitation not found", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "Invitations are not enabled", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.invitations.get", skip(state, authz), fields(%org_slug, %id))]
+pub async fn get(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path((org_slug, id)): Path<(String, Uuid)>,
+) -> Result<Json<Invitation>, AdminError> {
+    get_config(&state)?;
+    let services = get_services(&state)?;
+    let org = authorize_org(services, &authz, &org_slug, "read").await?;
+
+    Ok(Json(get_invitation(services, &org, id).await?))
+}
+
+/// Resend an invitation
+///
+/// Issues a new invite link with a fresh expiry and sends it again. The
+/// previous link stops working. Expired invitations can be resent; accepted
+/// and revoked ones cannot.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/organizations/{org_slug}/invitations/{id}/resend",
+    tag = "organizations",
+    operation_id = "invitation_resend",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("id" = Uuid, Path, description = "Invitation ID"),
+    ),
+    responses(
+        (status = 200, description = "Invitation resent", body = InvitationWithLink),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Invitation not found", body = crate::openapi::ErrorResponse),
+        (status = 409, description = "Invitation was already accepted or revoked", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "Invitations are not enabled", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.invitations.resend", skip(state, admin_auth, authz), fields(%org_slug, %id))]
+pub async fn resend(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path((org_slug, id)): Path<(String, Uuid)>,
+) -> Result<Json<InvitationWithLink>, AdminError> {
+    let config = get_config(&state)?;
+    let services = get_services(&state)?;
+    let org = authorize_org(services, &authz, &org_slug, "update").await?;
+    let actor = AuditActor::from(&admin_auth);
+
+    let invitation = get_invitation(services, &org, id).await?;
+    ensure_open(&invitation)?;
+
+    let (invitation, token) = services
+        .invitations
+        .resend(invitation.id, chrono::Utc::now() + config.ttl())
+        .await?;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "invitation.resend".to_string(),
+            resource_type: "invitation".to_string(),
+            resource_id: invitation.id,
+            org_id: Some(org.id),
+            project_id: None,
+            details: json!({
+                "email": invitation.email,
+                "expires_at": invitation.expires_at,
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(Json(
+        deliver(&state, services, config, &org, invitation, &token).await?,
+    ))
+}
+
+/// Revoke an invitation
+///
+/// The invite link stops working immediately. Accepted invitations can't be
+/// revoked; remove the member instead.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    delete,
+    path = "/admin/v1/organizations/{org_slug}/invitations/{id}",
+    tag = "organizations",
+    operation_id = "invitation_revoke",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("id" = Uuid, Path, description = "Invitation ID"),
+    ),
+    responses(
+        (status = 200, description = "Invitation revoked", body = Invitation),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Invitation not found", body = crate::openapi::ErrorResponse),
+        (status = 409, description = "Invitation was already accepted or revoked", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "Invitations are not enabled", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.invitations.revoke", skip(state, admin_auth, authz), fields(%org_slug, %id))]
+pub async fn revoke(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path((org_slug, id)): Path<(String, Uuid)>,
+) -> Result<Json<Invitation>, AdminError> {
+    get_config(&state)?;
+    let services = get_services(&state)?;
+    let org = authorize_org(services, &authz, &org_slug, "update").await?;
+    let actor = AuditActor::from(&admin_auth);
+
+    let invitation = get_invitation(services, &org, id).await?;
+    ensure_open(&invitation)?;
+    let invitation = services.invitations.revoke(invitation.id).await?;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "invitation.revoke".to_string(),
+            resource_type: "invitation".to_string(),
+            resource_id: invitation.id,
+            org_id: Some(org.id),
+            project_id: None,
+            details: json!({
+                "email": invitation.email,
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(Json(invitation))
+}
+
+/// Reject changes to invitations that were already accepted or revoked.
+fn ensure_open(invitation: &Invitation) -> Result<(), AdminError> {
+    match invitation.status {
+        InvitationStatus::Pending | InvitationStatus::Expired => Ok(()),
+        InvitationStatus::Accepted | InvitationStatus::Revoked => Err(AdminError::Conflict(
+            format!("Invitation is already {}", invitation.status.as_str()),
+        )),
+    }
+}
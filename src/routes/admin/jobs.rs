@@ -0,0 +1,249 @@
+//! Admin API endpoints for background jobs.
+//!
+//! Lists the jobs registered on the instance serving the request with their
+//! last and next runs, triggers and pauses them, and returns run history.
+//! Triggers act on this instance; pausing and run history are shared by all
+//! instances using the same database.
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use serde::{Deserialize, Serialize};
+
+use super::AdminError;
+use crate::{
+    AppState,
+    jobs::{JobStatus, RUN_HISTORY_LIMIT},
+    middleware::{AdminAuth, AuthzContext},
+    models::JobRun,
+};
+
+/// Background jobs registered on this instance.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct JobListResponse {
+    /// Jobs ordered by name
+    pub data: Vec<JobStatus>,
+}
+
+/// Query parameters for a job's run history.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema, utoipa::IntoParams))]
+pub struct JobRunsQuery {
+    /// Maximum number of runs to return (default: 20, max: 100).
+    pub limit: Option<i64>,
+}
+
+/// Recent runs of a background job.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct JobRunListResponse {
+    /// Runs, newest first
+    pub data: Vec<JobRun>,
+}
+
+fn not_found(name: &str) -> AdminError {
+    AdminError::NotFound(format!("Job '{name}' is not registered on this instance"))
+}
+
+async fn job_status(state: &AppState, name: &str) -> Result<JobStatus, AdminError> {
+    state
+        .job_scheduler
+        .get(name)
+        .await?
+        .ok_or_else(|| not_found(name))
+}
+
+/// List background jobs
+///
+/// Jobs only appear once their worker has started, so disabled jobs are
+/// missing. `last_run` is the most recent run on any instance.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/jobs",
+    tag = "jobs",
+    operation_id = "job_list",
+    responses(
+        (status = 200, description = "Background jobs", body = JobListResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn list(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+) -> Result<Json<JobListResponse>, AdminError> {
+    authz.require("jobs", "list", None, None, None, None)?;
+
+    let data = state.job_scheduler.list().await?;
+    Ok(Json(JobListResponse { data }))
+}
+
+/// Get a background job
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/jobs/{name}",
+    tag = "jobs",
+    operation_id = "job_get",
+    params(
+        ("name" = String, Path, description = "Job name"),
+    ),
+    responses(
+        (status = 200, description = "Background job", body = JobStatus),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Job not registered", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn get(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(name): Path<String>,
+) -> Result<Json<JobStatus>, AdminError> {
+    authz.require("jobs", "read", None, None, None, None)?;
+
+    Ok(Json(job_status(&state, &name).await?))
+}
+
+/// List a background job's runs
+///
+/// Up to the 100 most recent runs of each job are kept.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/jobs/{name}/runs",
+    tag = "jobs",
+    operation_id = "job_list_runs",
+    params(
+        ("name" = String, Path, description = "Job name"),
+        JobRunsQuery,
+    ),
+    responses(
+        (status = 200, description = "Job runs", body = JobRunListResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn list_runs(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(name): Path<String>,
+    Query(query): Query<JobRunsQuery>,
+) -> Result<Json<JobRunListResponse>, AdminError> {
+    authz.require("jobs", "read", None, None, None, None)?;
+
+    let limit = query.limit.unwrap_or(20).clamp(1, RUN_HISTORY_LIMIT);
+    let data = state.job_scheduler.history(&name, limit).await?;
+    Ok(Json(JobRunListResponse { data }))
+}
+
+/// Run a background job now
+///
+/// Runs the job on this instance as soon as it's idle, even if it's paused.
+/// If the job is already running, it runs again once the current run
+/// finishes. Jobs that run on one instance at a time skip the run when
+/// another instance holds the job's lock.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/jobs/{name}/run",
+    tag = "jobs",
+    operation_id = "job_run",
+    params(
+        ("name" = String, Path, description = "Job name"),
+    ),
+    responses(
+        (status = 202, description = "Run requested", body = JobStatus),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Job not registered", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn run(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(name): Path<String>,
+) -> Result<(StatusCode, Json<JobStatus>), AdminError> {
+    authz.require("jobs", "run", None, None, None, None)?;
+
+    if !state.job_scheduler.trigger(&name) {
+        return Err(not_found(&name));
+    }
+    tracing::info!(
+        job = %name,
+        user_id = ?admin_auth.identity.user_id,
+        "Job run requested through the admin API"
+    );
+
+    Ok((StatusCode::ACCEPTED, Json(job_status(&state, &name).await?)))
+}
+
+/// Pause a background job
+///
+/// Scheduled runs are skipped until the job is resumed. Manual runs still
+/// go ahead.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/jobs/{name}/pause",
+    tag = "jobs",
+    operation_id = "job_pause",
+    params(
+        ("name" = String, Path, description = "Job name"),
+    ),
+    responses(
+        (status = 200, description = "Job paused", body = JobStatus),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Job not registered", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn pause(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(name): Path<String>,
+) -> Result<Json<JobStatus>, AdminError> {
+    set_paused(state, admin_auth, authz, name, true).await
+}
+
+/// Resume a paused background job
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/jobs/{name}/resume",
+    tag = "jobs",
+    operation_id = "job_resume",
+    params(
+        ("name" = String, Path, description = "Job name"),
+    ),
+    responses(
+        (status = 200, description = "Job resumed", body = JobStatus),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Job not registered", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn resume(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(name): Path<String>,
+) -> Result<Json<JobStatus>, AdminError> {
+    set_paused(state, admin_auth, authz, name, false).await
+}
+
+async fn set_paused(
+    state: AppState,
+    admin_auth: AdminAuth,
+    authz: AuthzContext,
+    name: String,
+    paused: bool,
+) -> Result<Json<JobStatus>, AdminError> {
+    authz.require("jobs", "update", None, None, None, None)?;
+
+    if !state.job_scheduler.set_paused(&name, paused).await? {
+        return Err(not_found(&name));
+    }
+    tracing::info!(
+        job = %name,
+        paused,
+        user_id = ?admin_auth.identity.user_id,
+        "Job pause state changed through the admin API"
+    );
+
+    Ok(Json(job_status(&state, &name).await?))
+}
@@ -13,13 +13,14 @@ use super::{
         ApiKeyListResponse, RotateApiKeyRequest, invalidate_api_key_cache, validate_api_key_input,
     },
     error::AdminError,
-    organizations::ListQuery,
+    organizations::{LabelQuery, ListQuery},
 };
 use crate::{
     AppState,
     middleware::{AdminAuth, AuthzContext, ClientInfo},
     models::{
-        ApiKey, ApiKeyOwner, CreateApiKey, CreateAuditLog, CreateSelfServiceApiKey, CreatedApiKey,
+        ApiKey, ApiKeyHasher, ApiKeyOwner, CreateApiKey, CreateAuditLog, CreateSelfServiceApiKey,
+        CreatedApiKey,
     },
     openapi::PaginationMeta,
     services::Services,
@@ -87,7 +88,7 @@ pub async fn get(
     path = "/admin/v1/me/api-keys",
     tag = "me",
     operation_id = "me_api_keys_list",
-    params(ListQuery),
+    params(ListQuery, LabelQuery),
     responses(
         (status = 200, description = "List of user's API keys", body = ApiKeyListResponse),
         (status = 401, description = "User not identified from session", body = crate::openapi::ErrorResponse),
@@ -98,13 +99,14 @@ pub async fn list(
     Extension(admin_auth): Extension<AdminAuth>,
     Extension(authz): Extension<AuthzContext>,
     Query(query): Query<ListQuery>,
+    Query(labels): Query<LabelQuery>,
 ) -> Result<Json<ApiKeyListResponse>, AdminError> {
     authz.require("api_key", "self_list", None, None, None, None)?;
     let user_id = get_user_id(&admin_auth)?;
     let services = get_services(&state)?;
 
     let limit = query.limit.unwrap_or(100);
-    let params = query.try_into_with_cursor()?;
+    let params = labels.apply(query.try_into_with_cursor()?)?;
 
     let result = services.api_keys.list_by_user(user_id, params).await?;
 
@@ -168,7 +170,9 @@ pub async fn create(
     }
 
     // Get the key generation prefix from config
-    let prefix = state.config.auth.api_key_config().generation_prefix();
+    let api_key_config = state.config.auth.api_key_config();
+    let prefix = api_key_config.generation_prefix();
+    let hasher = ApiKeyHasher::from_config(api_key_config);
 
     let create_input = CreateApiKey {
         name: input.name,
@@ -184,7 +188,10 @@ pub async fn create(
         sovereignty_requirements: input.sovereignty_requirements,
     };
 
-    let created = services.api_keys.create(create_input, &prefix).await?;
+    let created = services
+        .api_keys
+        .create(create_input, &prefix, &hasher)
+        .await?;
 
     // Audit log (fire-and-forget)
     let _ = services
@@ -324,12 +331,14 @@ pub async fn rotate(
     }
 
     // Get the key generation prefix from config
-    let prefix = state.config.auth.api_key_config().generation_prefix();
+    let api_key_config = state.config.auth.api_key_config();
+    let prefix = api_key_config.generation_prefix();
+    let hasher = ApiKeyHasher::from_config(api_key_config);
 
     // Perform the rotation
     let created = services
         .api_keys
-        .rotate(key_id, grace_period_seconds, &prefix)
+        .rotate(key_id, grace_period_seconds, &prefix, &hasher)
         .await?;
 
     // Audit log (fire-and-forget)
@@ -8,7 +8,11 @@ use serde::{Deserialize, Serialize};
 use serde_json::json;
 use uuid::Uuid;
 
-use super::{AuditActor, error::AdminError, organizations::ListQuery};
+use super::{
+    AuditActor,
+    error::AdminError,
+    organizations::{LabelQuery, ListQuery},
+};
 use crate::{
     AppState,
     middleware::{AdminAuth, AuthzContext, ClientInfo},
@@ -66,7 +70,7 @@ async fn verify_user_owns_provider(
     path = "/admin/v1/me/providers",
     tag = "me",
     operation_id = "me_providers_list",
-    params(ListQuery),
+    params(ListQuery, LabelQuery),
     responses(
         (status = 200, description = "List of user's dynamic providers", body = SelfServiceProviderListResponse),
         (status = 401, description = "User not identified from session", body = crate::openapi::ErrorResponse),
@@ -77,13 +81,14 @@ pub async fn list(
     Extension(admin_auth): Extension<AdminAuth>,
     Extension(authz): Extension<AuthzContext>,
     Query(query): Query<ListQuery>,
+    Query(labels): Query<LabelQuery>,
 ) -> Result<Json<SelfServiceProviderListResponse>, AdminError> {
     authz.require("dynamic_provider", "self_list", None, None, None, None)?;
     let user_id = get_user_id(&admin_auth)?;
     let services = get_services(&state)?;
 
     let limit = query.limit.unwrap_or(100);
-    let params = query.try_into_with_cursor()?;
+    let params = labels.apply(query.try_into_with_cursor()?)?;
 
     let result = services.providers.list_by_user(user_id, params).await?;
 
@@ -427,6 +432,7 @@ pub async fn test_credentials(
         models: input.models.unwrap_or_default(),
         sovereignty: input.sovereignty,
         is_enabled: true,
+        labels: Default::default(),
         created_at: chrono::Utc::now(),
         updated_at: chrono::Utc::now(),
     };
@@ -1,6 +1,11 @@
 pub mod access_reviews;
+#[cfg(feature = "server")]
+pub mod agents;
 pub mod api_keys;
 pub mod audit_logs;
+pub mod bulk;
+pub mod bundles;
+pub mod client_cert_mappings;
 pub mod conversations;
 #[cfg(feature = "csv-export")]
 pub(super) mod csv_export;
@@ -9,35 +14,65 @@ pub mod dlq;
 pub mod domain_verifications;
 #[cfg(feature = "server")]
 pub mod dynamic_providers;
+pub mod elevations;
+pub mod email_log;
 mod error;
+pub mod federation;
+#[cfg(feature = "graphql")]
+pub mod graphql;
+#[cfg(feature = "server")]
+pub mod http_tools;
+pub mod invitations;
+pub mod jobs;
 pub mod me;
 pub mod me_api_keys;
 pub mod me_providers;
 #[cfg(feature = "sso")]
 pub mod me_sessions;
+pub mod model_catalog;
 pub mod model_pricing;
 pub mod oauth;
+pub mod observability;
 pub mod org_rbac_policies;
+pub mod org_request_policies;
 #[cfg(feature = "sso")]
 pub mod org_sso_configs;
 pub mod organizations;
 pub mod projects;
+#[cfg(feature = "server")]
+pub mod provenance;
 pub mod providers;
+pub mod reconciliation;
+#[cfg(feature = "server")]
+pub mod replay;
+pub mod report_runs;
+#[cfg(feature = "server")]
+pub mod request_tail;
+#[cfg(feature = "server")]
+pub mod scheduled_tasks;
 #[cfg(feature = "sso")]
 pub mod scim_configs;
+pub mod semantic_cache;
 pub mod service_accounts;
 pub mod session_info;
 #[cfg(feature = "sso")]
 pub mod sessions;
+#[cfg(feature = "server")]
+pub mod shadow_results;
 #[cfg(feature = "sso")]
 pub mod sso_connections;
 #[cfg(feature = "sso")]
 pub mod sso_group_mappings;
+pub mod step_up;
+pub mod system;
 pub mod teams;
 pub mod templates;
 pub mod ui_config;
 pub mod usage;
+#[cfg(feature = "server")]
+pub mod usage_anomalies;
 pub mod users;
+pub mod vector_store_syncs;
 
 #[cfg(any(feature = "server", feature = "wasm"))]
 use axum::Router;
@@ -116,6 +151,12 @@ pub(crate) fn admin_v1_routes() -> Router<AppState> {
             get(me_api_keys::get).merge(delete(me_api_keys::revoke)),
         )
         .route("/me/api-keys/{key_id}/rotate", post(me_api_keys::rotate))
+        // Step-up authentication (TOTP) for high-risk admin operations
+        .route("/me/step-up", get(step_up::status))
+        .route("/me/step-up/totp", delete(step_up::remove))
+        .route("/me/step-up/totp/enroll", post(step_up::enroll))
+        .route("/me/step-up/totp/confirm", post(step_up::confirm))
+        .route("/me/step-up/verify", post(step_up::verify))
         // OAuth-style PKCE flow for issuing user-scoped keys to external apps
         .route("/oauth/authorize", post(oauth::authorize))
         .route("/oauth/preflight", get(oauth::preflight))
@@ -130,6 +171,48 @@ pub(crate) fn admin_v1_routes() -> Router<AppState> {
                 .merge(patch(organizations::update))
                 .merge(delete(organizations::delete)),
         )
+        .route(
+            "/organizations/{slug}/semantic-cache",
+            get(semantic_cache::get).merge(delete(semantic_cache::purge)),
+        )
+        .route(
+            "/organizations/{slug}/network-policy",
+            get(organizations::get_network_policy)
+                .merge(put(organizations::set_network_policy))
+                .merge(delete(organizations::delete_network_policy)),
+        )
+        .route(
+            "/organizations/{slug}/parameter-governance",
+            get(organizations::get_parameter_governance)
+                .merge(put(organizations::set_parameter_governance))
+                .merge(delete(organizations::delete_parameter_governance)),
+        )
+        .route(
+            "/organizations/{slug}/model-access",
+            get(organizations::get_model_access)
+                .merge(put(organizations::set_model_access))
+                .merge(delete(organizations::delete_model_access)),
+        )
+        .route(
+            "/organizations/{slug}/model-degradation",
+            get(organizations::get_model_degradation)
+                .merge(put(organizations::set_model_degradation))
+                .merge(delete(organizations::delete_model_degradation)),
+        )
+        .route(
+            "/organizations/{slug}/entitlements",
+            get(organizations::get_entitlements)
+                .merge(put(organizations::set_entitlements))
+                .merge(delete(organizations::delete_entitlements)),
+        )
+        .route(
+            "/organizations/{slug}/labels",
+            put(organizations::set_labels),
+        )
+        .route(
+            "/organizations/{slug}/allowed-models",
+            get(organizations::allowed_models),
+        )
         // Projects
         .route(
             "/organizations/{org_slug}/projects",
@@ -141,6 +224,22 @@ pub(crate) fn admin_v1_routes() -> Router<AppState> {
                 .merge(patch(projects::update))
                 .merge(delete(projects::delete)),
         )
+        .route(
+            "/organizations/{org_slug}/projects/{project_slug}/labels",
+            put(projects::set_labels),
+        )
+        .route(
+            "/organizations/{org_slug}/projects/{project_slug}/parameter-governance",
+            get(projects::get_parameter_governance)
+                .merge(put(projects::set_parameter_governance))
+                .merge(delete(projects::delete_parameter_governance)),
+        )
+        .route(
+            "/organizations/{org_slug}/projects/{project_slug}/model-access",
+            get(projects::get_model_access)
+                .merge(put(projects::set_model_access))
+                .merge(delete(projects::delete_model_access)),
+        )
         // Teams
         .route(
             "/organizations/{org_slug}/teams",
@@ -172,8 +271,17 @@ pub(crate) fn admin_v1_routes() -> Router<AppState> {
                 .merge(patch(service_accounts::update))
                 .merge(delete(service_accounts::delete)),
         )
+        .route(
+            "/organizations/{org_slug}/service-accounts/{sa_slug}/client-certs",
+            post(client_cert_mappings::create).merge(get(client_cert_mappings::list)),
+        )
+        .route(
+            "/organizations/{org_slug}/service-accounts/{sa_slug}/client-certs/{id}",
+            delete(client_cert_mappings::delete),
+        )
         // Users (top-level)
         .route("/users", post(users::create).merge(get(users::list)))
+        .route("/users/bulk", post(bulk::create_users))
         .route(
             "/users/{user_id}",
             get(users::get)
@@ -186,10 +294,48 @@ pub(crate) fn admin_v1_routes() -> Router<AppState> {
             "/organizations/{org_slug}/members",
             get(users::list_org_members).merge(post(users::add_org_member)),
         )
+        .route(
+            "/organizations/{org_slug}/members/bulk",
+            post(bulk::add_org_members),
+        )
         .route(
             "/organizations/{org_slug}/members/{user_id}",
             delete(users::remove_org_member).merge(patch(users::update_org_member)),
         )
+        // Invitations
+        .route(
+            "/organizations/{org_slug}/invitations",
+            post(invitations::create).merge(get(invitations::list)),
+        )
+        .route(
+            "/organizations/{org_slug}/invitations/{id}",
+            get(invitations::get).merge(delete(invitations::revoke)),
+        )
+        .route(
+            "/organizations/{org_slug}/invitations/{id}/resend",
+            post(invitations::resend),
+        )
+        // Role elevations (break-glass access)
+        .route(
+            "/organizations/{org_slug}/elevations",
+            post(elevations::create).merge(get(elevations::list)),
+        )
+        .route(
+            "/organizations/{org_slug}/elevations/{id}",
+            get(elevations::get),
+        )
+        .route(
+            "/organizations/{org_slug}/elevations/{id}/approve",
+            post(elevations::approve),
+        )
+        .route(
+            "/organizations/{org_slug}/elevations/{id}/deny",
+            post(elevations::deny),
+        )
+        .route(
+            "/organizations/{org_slug}/elevations/{id}/revoke",
+            post(elevations::revoke),
+        )
         // Project memberships
         .route(
             "/organizations/{org_slug}/projects/{project_slug}/members",
@@ -201,8 +347,10 @@ pub(crate) fn admin_v1_routes() -> Router<AppState> {
         )
         // API Keys
         .route("/api-keys", post(api_keys::create))
+        .route("/api-keys/bulk-revoke", post(bulk::revoke_api_keys))
         .route("/api-keys/{key_id}", delete(api_keys::revoke))
         .route("/api-keys/{key_id}/rotate", post(api_keys::rotate))
+        .route("/api-keys/{key_id}/labels", put(api_keys::set_labels))
         .route(
             "/organizations/{org_slug}/api-keys",
             get(api_keys::list_by_org),
@@ -226,6 +374,10 @@ pub(crate) fn admin_v1_routes() -> Router<AppState> {
                 .patch(dynamic_providers::update)
                 .delete(dynamic_providers::delete),
         )
+        .route(
+            "/dynamic-providers/{id}/labels",
+            put(dynamic_providers::set_labels),
+        )
         .route(
             "/dynamic-providers/{id}/test",
             post(dynamic_providers::test_connectivity),
@@ -246,6 +398,80 @@ pub(crate) fn admin_v1_routes() -> Router<AppState> {
             "/users/{user_id}/dynamic-providers",
             get(dynamic_providers::list_by_user),
         );
+    // Agents (requires server feature — runs need the tool-use pipeline)
+    #[cfg(feature = "server")]
+    let router = router
+        .route(
+            "/organizations/{org_slug}/agents",
+            get(agents::list).merge(post(agents::create)),
+        )
+        .route(
+            "/organizations/{org_slug}/agents/{agent_id}",
+            get(agents::get)
+                .merge(patch(agents::update))
+                .merge(delete(agents::delete)),
+        );
+    // HTTP tools (requires server feature — calls run in the tool-use pipeline)
+    #[cfg(feature = "server")]
+    let router = router
+        .route(
+            "/organizations/{org_slug}/http-tools",
+            get(http_tools::list).merge(post(http_tools::create)),
+        )
+        .route(
+            "/organizations/{org_slug}/http-tools/{tool_id}",
+            get(http_tools::get)
+                .merge(patch(http_tools::update))
+                .merge(delete(http_tools::delete)),
+        );
+    // Scheduled tasks (requires server feature — runs use the execution pipeline)
+    #[cfg(feature = "server")]
+    let router = router
+        .route(
+            "/organizations/{org_slug}/scheduled-tasks",
+            get(scheduled_tasks::list).merge(post(scheduled_tasks::create)),
+        )
+        .route(
+            "/organizations/{org_slug}/scheduled-tasks/{task_id}",
+            get(scheduled_tasks::get)
+                .merge(patch(scheduled_tasks::update))
+                .merge(delete(scheduled_tasks::delete)),
+        )
+        .route(
+            "/organizations/{org_slug}/scheduled-tasks/{task_id}/runs",
+            get(scheduled_tasks::list_runs),
+        );
+    // Provenance verification (requires server feature — metadata is signed by
+    // the API middleware)
+    #[cfg(feature = "server")]
+    let router = router.route("/provenance/verify", post(provenance::verify));
+    // Response replay (requires server feature — needs the responses store)
+    #[cfg(feature = "server")]
+    let router = router.route(
+        "/organizations/{org_slug}/responses/{response_id}/replay",
+        post(replay::replay),
+    );
+    // Shadow traffic results (requires server feature — recorded by shadow traffic)
+    #[cfg(feature = "server")]
+    let router = router
+        .route(
+            "/shadow-results",
+            get(shadow_results::list).merge(delete(shadow_results::delete)),
+        )
+        .route("/shadow-results/report", get(shadow_results::report))
+        .route("/shadow-results/{id}", get(shadow_results::get));
+    // Usage anomalies (requires server feature — recorded by anomaly detection)
+    #[cfg(feature = "server")]
+    let router = router
+        .route("/anomalies", get(usage_anomalies::list))
+        .route("/anomalies/{id}", get(usage_anomalies::get))
+        .route(
+            "/anomalies/{id}/acknowledge",
+            post(usage_anomalies::acknowledge),
+        );
+    // Live request tail (requires server feature — fed by the API middleware)
+    #[cfg(feature = "server")]
+    let router = router.route("/requests/tail", get(request_tail::tail));
     // Usage endpoints - API Key level
     let router = router
         .route("/api-keys/{key_id}/usage", get(usage::get_summary))
@@ -340,6 +566,18 @@ pub(crate) fn admin_v1_routes() -> Router<AppState> {
             "/organizations/{slug}/usage/forecast",
             get(usage::get_org_forecast),
         )
+        .route(
+            "/organizations/{slug}/usage/reliability",
+            get(usage::get_org_reliability),
+        )
+        .route(
+            "/organizations/{slug}/usage/by-tag",
+            get(usage::get_org_by_tag),
+        )
+        .route(
+            "/organizations/{slug}/usage/by-date-tag",
+            get(usage::get_org_by_date_tag),
+        )
         // Usage endpoints - Project level
         .route(
             "/organizations/{org_slug}/projects/{project_slug}/usage",
@@ -385,6 +623,18 @@ pub(crate) fn admin_v1_routes() -> Router<AppState> {
             "/organizations/{org_slug}/projects/{project_slug}/usage/forecast",
             get(usage::get_project_forecast),
         )
+        .route(
+            "/organizations/{org_slug}/projects/{project_slug}/usage/reliability",
+            get(usage::get_project_reliability),
+        )
+        .route(
+            "/organizations/{org_slug}/projects/{project_slug}/usage/by-tag",
+            get(usage::get_project_by_tag),
+        )
+        .route(
+            "/organizations/{org_slug}/projects/{project_slug}/usage/by-date-tag",
+            get(usage::get_project_by_date_tag),
+        )
         // Usage endpoints - User level
         .route("/users/{user_id}/usage", get(usage::get_user_summary))
         .route(
@@ -538,8 +788,37 @@ pub(crate) fn admin_v1_routes() -> Router<AppState> {
         .route("/usage/by-date-team", get(usage::get_global_by_date_team))
         .route("/usage/by-org", get(usage::get_global_by_org))
         .route("/usage/by-date-org", get(usage::get_global_by_date_org))
+        .route("/usage/reconciliation", get(usage::get_reconciliation))
+        .route("/usage/reliability", get(usage::get_global_reliability))
+        .route("/usage/by-tag", get(usage::get_global_by_tag))
+        .route("/usage/by-date-tag", get(usage::get_global_by_date_tag))
         .route("/usage/logs", get(usage::list_logs))
         .route("/usage/logs/export", get(usage::export_logs))
+        // Provider usage imports and variance report
+        .route("/reconciliation", get(reconciliation::report))
+        .route(
+            "/reconciliation/imports",
+            get(reconciliation::list_imports).merge(post(reconciliation::import_from_api)),
+        )
+        .route(
+            "/reconciliation/imports/csv",
+            post(reconciliation::import_csv),
+        )
+        .route(
+            "/reconciliation/imports/{id}",
+            delete(reconciliation::delete_import),
+        )
+        // Custom model catalog entries
+        .route(
+            "/model-catalog",
+            get(model_catalog::list).merge(post(model_catalog::create)),
+        )
+        .route(
+            "/model-catalog/{id}",
+            get(model_catalog::get)
+                .merge(put(model_catalog::update))
+                .merge(delete(model_catalog::delete)),
+        )
         // Model Pricing
         .route(
             "/model-pricing",
@@ -582,6 +861,19 @@ pub(crate) fn admin_v1_routes() -> Router<AppState> {
             post(conversations::append_messages),
         )
         .route("/conversations/{id}/pin", put(conversations::set_pin))
+        .route(
+            "/conversations/{id}/branches",
+            get(conversations::list_branches).merge(post(conversations::fork)),
+        )
+        .route(
+            "/conversations/{id}/branches/active",
+            put(conversations::switch_branch),
+        )
+        .route(
+            "/conversations/{id}/attachments/{file_id}",
+            get(conversations::get_attachment),
+        )
+        .route("/conversations/{id}/replay", get(conversations::replay))
         .route(
             "/organizations/{org_slug}/projects/{project_slug}/conversations",
             get(conversations::list_by_project),
@@ -602,6 +894,7 @@ pub(crate) fn admin_v1_routes() -> Router<AppState> {
                 .merge(patch(templates::update))
                 .merge(delete(templates::delete)),
         )
+        .route("/templates/{id}/labels", put(templates::set_labels))
         .route(
             "/organizations/{org_slug}/templates",
             get(templates::list_by_org),
@@ -625,6 +918,16 @@ pub(crate) fn admin_v1_routes() -> Router<AppState> {
             "/providers/{provider_name}/circuit-breaker",
             get(providers::get_circuit_breaker),
         )
+        .route(
+            "/providers/{provider_name}/concurrency",
+            get(providers::get_concurrency),
+        )
+        .route(
+            "/providers/{provider_name}/settings",
+            get(providers::get_provider_settings)
+                .merge(put(providers::update_provider_settings))
+                .merge(delete(providers::delete_provider_settings)),
+        )
         .route("/providers/health", get(providers::list_provider_health))
         .route(
             "/providers/{provider_name}/health",
@@ -640,15 +943,52 @@ pub(crate) fn admin_v1_routes() -> Router<AppState> {
             "/providers/{provider_name}/stats/history",
             get(providers::get_provider_stats_history),
         )
+        // Observability provisioning
+        .route("/observability/grafana", get(observability::grafana))
+        .route("/slos", get(observability::slos))
+        // Instance lifecycle
+        .route(
+            "/system/drain",
+            get(system::drain_status).merge(post(system::drain)),
+        )
+        .route("/system/jobs", get(system::jobs))
+        // Background jobs
+        .route("/jobs", get(jobs::list))
+        .route("/jobs/{name}", get(jobs::get))
+        .route("/jobs/{name}/runs", get(jobs::list_runs))
+        .route("/jobs/{name}/run", post(jobs::run))
+        .route("/jobs/{name}/pause", post(jobs::pause))
+        .route("/jobs/{name}/resume", post(jobs::resume))
+        // Bundles
+        .route("/export", get(bundles::export))
+        .route("/import", post(bundles::import))
         // Dead Letter Queue
         .route("/dlq", get(dlq::list).merge(delete(dlq::purge)))
         .route("/dlq/stats", get(dlq::stats))
         .route("/dlq/prune", post(dlq::prune))
+        .route("/dlq/redrive", post(dlq::redrive))
         .route("/dlq/{id}", get(dlq::get).merge(delete(dlq::delete)))
         .route("/dlq/{id}/retry", post(dlq::retry))
         // Audit Logs
         .route("/audit-logs", get(audit_logs::list))
         .route("/audit-logs/{id}", get(audit_logs::get))
+        // Email send log
+        .route("/email-log", get(email_log::list))
+        // Scheduled Reports
+        .route("/report-runs", get(report_runs::list))
+        .route("/report-runs/{id}", get(report_runs::get))
+        .route("/vector-stores/{id}/syncs", get(vector_store_syncs::list))
+        .route(
+            "/vector-stores/{id}/syncs/{sync_id}",
+            get(vector_store_syncs::get),
+        )
+        // Federation hub
+        .route("/federation/gateways", get(federation::list_gateways))
+        .route(
+            "/federation/gateways/{id}",
+            get(federation::get_gateway).merge(delete(federation::delete_gateway)),
+        )
+        .route("/federation/summary", get(federation::usage_summary))
         // Access Reviews
         .route(
             "/access-reviews/inventory",
@@ -689,7 +1029,22 @@ pub(crate) fn admin_v1_routes() -> Router<AppState> {
             "/organizations/{org_slug}/rbac-policies/simulate",
             post(org_rbac_policies::simulate),
         )
-        .route("/rbac-policies/validate", post(org_rbac_policies::validate));
+        .route("/rbac-policies/validate", post(org_rbac_policies::validate))
+        // Organization Request Policies
+        .route(
+            "/organizations/{org_slug}/request-policies",
+            get(org_request_policies::list).merge(post(org_request_policies::create)),
+        )
+        .route(
+            "/organizations/{org_slug}/request-policies/simulate",
+            post(org_request_policies::simulate),
+        )
+        .route(
+            "/organizations/{org_slug}/request-policies/{policy_id}",
+            get(org_request_policies::get)
+                .merge(patch(org_request_policies::update))
+                .merge(delete(org_request_policies::delete)),
+        );
 
     // Session info (available in all builds including WASM)
     let router = router.route("/session-info", get(session_info::get));
@@ -709,6 +1064,10 @@ pub(crate) fn admin_v1_routes() -> Router<AppState> {
             "/users/{user_id}/sessions/{session_id}",
             delete(sessions::delete_one),
         )
+        .route(
+            "/organizations/{org_slug}/force-logout",
+            post(sessions::force_logout),
+        )
         // SSO Connections (read-only, from config)
         .route("/sso-connections", get(sso_connections::list))
         .route("/sso-connections/{name}", get(sso_connections::get))
@@ -743,6 +1102,14 @@ pub(crate) fn admin_v1_routes() -> Router<AppState> {
                 .patch(org_sso_configs::update)
                 .delete(org_sso_configs::delete),
         )
+        .route(
+            "/organizations/{org_slug}/sso-config/role-rules",
+            get(org_sso_configs::get_role_rules).put(org_sso_configs::set_role_rules),
+        )
+        .route(
+            "/organizations/{org_slug}/sso-config/role-rules/evaluate",
+            post(org_sso_configs::evaluate_role_rules),
+        )
         // Domain Verifications (nested under org SSO config)
         .route(
             "/organizations/{org_slug}/sso-config/domains",
@@ -800,6 +1167,11 @@ mod tests {
     /// Create a test application with an in-memory database
     /// Each call creates a unique database to avoid test interference
     async fn test_app() -> axum::Router {
+        test_app_with_state().await.0
+    }
+
+    /// Like [`test_app`], also returning the state behind the router.
+    async fn test_app_with_state() -> (axum::Router, crate::AppState) {
         use std::sync::atomic::{AtomicU64, Ordering};
 
         // Initialize tracing for tests
@@ -838,7 +1210,7 @@ api_key = "sk-test-key"
         let state = crate::AppState::new(config.clone())
             .await
             .expect("Failed to create AppState");
-        crate::build_app(&config, state)
+        (crate::build_app(&config, state.clone()), state)
     }
 
     /// Helper to make a JSON POST request
@@ -877,6 +1249,24 @@ api_key = "sk-test-key"
         (status, json)
     }
 
+    /// Helper to make a JSON PUT request
+    async fn put_json(app: &axum::Router, uri: &str, body: Value) -> (StatusCode, Value) {
+        let request = Request::builder()
+            .method("PUT")
+            .uri(uri)
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_string(&body).unwrap()))
+            .unwrap();
+
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+        (status, json)
+    }
+
     /// Helper to make a GET request
     async fn get_json(app: &axum::Router, uri: &str) -> (StatusCode, Value) {
         let request = Request::builder()
@@ -993,6 +1383,27 @@ api_key = "sk-test-key"
         assert!(body["error"]["message"].is_string());
     }
 
+    #[tokio::test]
+    async fn test_semantic_cache_requires_semantic_caching() {
+        let app = test_app().await;
+        let (status, _) = post_json(
+            &app,
+            "/admin/v1/organizations",
+            json!({"slug": "cache-org", "name": "Cache Org"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let (status, body) =
+            get_json(&app, "/admin/v1/organizations/cache-org/semantic-cache").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["error"]["code"], "not_configured");
+
+        let (status, _) =
+            delete_json(&app, "/admin/v1/organizations/cache-org/semantic-cache").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    }
+
     #[tokio::test]
     async fn test_list_organizations() {
         let app = test_app().await;
@@ -1391,6 +1802,131 @@ api_key = "sk-test-key"
         assert_eq!(status, StatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn test_bulk_create_users_into_org() {
+        let app = test_app().await;
+        create_org(&app, "bulk-users-org").await;
+        create_user_with_id(&app, "bulk-existing").await;
+
+        let (status, body) = post_json(
+            &app,
+            "/admin/v1/users/bulk",
+            json!({
+                "org_slug": "bulk-users-org",
+                "role": "admin",
+                "users": [
+                    {"external_id": "bulk-new-1", "email": "one@example.com"},
+                    {"external_id": "bulk-existing"},
+                    {"external_id": "bulk-new-2", "email": "not-an-email"},
+                    {"external_id": "bulk-new-3"}
+                ]
+            }),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["succeeded"], 2);
+        assert_eq!(body["failed"], 2);
+        let results = body["results"].as_array().unwrap();
+        assert_eq!(results[0]["status"], "succeeded");
+        assert_eq!(results[1]["error"]["code"], "conflict");
+        assert_eq!(results[2]["error"]["code"], "validation_error");
+        assert_eq!(results[3]["status"], "succeeded");
+
+        let (_, members) = get_json(&app, "/admin/v1/organizations/bulk-users-org/members").await;
+        assert_eq!(members["data"].as_array().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_add_org_members_partial_failure() {
+        let app = test_app().await;
+        create_org(&app, "bulk-members-org").await;
+        let user_a = create_user_with_id(&app, "bulk-member-a").await;
+        let user_b = create_user_with_id(&app, "bulk-member-b").await;
+
+        let (status, body) = post_json(
+            &app,
+            "/admin/v1/organizations/bulk-members-org/members/bulk",
+            json!({"members": [
+                {"user_id": user_a},
+                {"user_id": user_a},
+                {"user_id": user_b, "role": "admin"}
+            ]}),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["succeeded"], 2);
+        assert_eq!(body["failed"], 1);
+        assert_eq!(body["results"][1]["id"], user_a);
+        assert_eq!(body["results"][1]["error"]["code"], "conflict");
+
+        let (status, _) = post_json(
+            &app,
+            "/admin/v1/organizations/no-such-org/members/bulk",
+            json!({"members": []}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_revoke_api_keys() {
+        let app = test_app().await;
+        let org_id = create_org_with_id(&app, "bulk-revoke-org").await;
+        let key_a = create_api_key_with_id(&app, &org_id).await;
+        let key_b = create_api_key_with_id(&app, &org_id).await;
+        let owner = json!({"type": "organization", "org_id": org_id});
+
+        // Fresh keys are not old enough to match the age filter
+        let (status, body) = post_json(
+            &app,
+            "/admin/v1/api-keys/bulk-revoke",
+            json!({"owner": owner, "older_than_days": 30}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body["results"].as_array().unwrap().is_empty());
+
+        // A dry run reports both keys without revoking them
+        let (status, body) = post_json(
+            &app,
+            "/admin/v1/api-keys/bulk-revoke",
+            json!({"owner": owner, "unused_for_days": 0, "dry_run": true}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["skipped"], 2);
+
+        let (status, body) = post_json(
+            &app,
+            "/admin/v1/api-keys/bulk-revoke",
+            json!({"owner": owner}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["succeeded"], 2);
+        let mut revoked: Vec<String> = body["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r["id"].as_str().unwrap().to_string())
+            .collect();
+        revoked.sort();
+        let mut expected = vec![key_a, key_b];
+        expected.sort();
+        assert_eq!(revoked, expected);
+
+        // Revoked keys no longer match
+        let (_, body) = post_json(
+            &app,
+            "/admin/v1/api-keys/bulk-revoke",
+            json!({"owner": owner}),
+        )
+        .await;
+        assert!(body["results"].as_array().unwrap().is_empty());
+    }
+
     // ============================================================================
     // Dynamic Provider Tests
     // ============================================================================
@@ -1730,48 +2266,185 @@ api_key = "sk-test-key"
     }
 
     #[tokio::test]
-    async fn test_append_messages() {
+    async fn test_conversation_branch_edit_and_switch() {
         let app = test_app().await;
-        let user_id = create_user_with_id(&app, "append-msg-user").await;
+        let user_id = create_user_with_id(&app, "branch-conv-user").await;
 
         let (status, created) = post_json(
             &app,
             "/admin/v1/conversations",
             json!({
                 "owner": {"type": "user", "user_id": user_id},
-                "title": "Message Append Test",
-                "messages": [{"role": "user", "content": "First message"}]
+                "title": "Branching",
+                "messages": [
+                    {"role": "user", "content": "Hello"},
+                    {"role": "assistant", "content": "Hi there"}
+                ]
             }),
         )
         .await;
         assert_eq!(status, StatusCode::CREATED);
-
         let conv_id = created["id"].as_str().unwrap();
-        let (status, body) = post_json(
+        let branches_uri = format!("/admin/v1/conversations/{}/branches", conv_id);
+
+        let (status, body) = get_json(&app, &branches_uri).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.as_array().unwrap().is_empty());
+
+        // Edit the first message
+        let (status, branch) = post_json(
             &app,
-            &format!("/admin/v1/conversations/{}/messages", conv_id),
-            json!({
-                "messages": [
-                    {"role": "assistant", "content": "Second message"},
-                    {"role": "user", "content": "Third message"}
-                ]
-            }),
+            &branches_uri,
+            json!({"message_index": 0, "message": {"role": "user", "content": "Howdy"}}),
         )
         .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(branch["is_active"], true);
+        assert_eq!(branch["messages"].as_array().unwrap().len(), 1);
 
-        assert_eq!(status, StatusCode::OK);
-        assert_eq!(body.as_array().unwrap().len(), 3);
-        assert_eq!(body[0]["content"], "First message");
-        assert_eq!(body[1]["content"], "Second message");
-        assert_eq!(body[2]["content"], "Third message");
-    }
+        let (_, conv) = get_json(&app, &format!("/admin/v1/conversations/{}", conv_id)).await;
+        assert_eq!(conv["messages"][0]["content"], "Howdy");
 
-    #[tokio::test]
-    async fn test_delete_conversation() {
-        let app = test_app().await;
-        let user_id = create_user_with_id(&app, "delete-conv-user").await;
+        // Switch back to the original history
+        let (_, branches) = get_json(&app, &branches_uri).await;
+        let branches = branches.as_array().unwrap();
+        assert_eq!(branches.len(), 2);
+        let root_id = branches[0]["id"].as_str().unwrap();
 
-        let (status, created) = post_json(
+        let (status, conv) = put_json(
+            &app,
+            &format!("{}/active", branches_uri),
+            json!({"branch_id": root_id}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(conv["messages"].as_array().unwrap().len(), 2);
+        assert_eq!(conv["messages"][0]["content"], "Hello");
+
+        // Out-of-range index is rejected
+        let (status, _) = post_json(&app, &branches_uri, json!({"message_index": 5})).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        // Unknown branch
+        let (status, _) = put_json(
+            &app,
+            &format!("{}/active", branches_uri),
+            json!({"branch_id": uuid::Uuid::new_v4()}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_conversation_attachments_and_replay() {
+        let app = test_app().await;
+        let user_id = create_user_with_id(&app, "attachment-conv-user").await;
+        let missing_file = format!("file-{}", uuid::Uuid::new_v4());
+
+        // Attachments must reference a file owned by the conversation owner
+        let (status, _) = post_json(
+            &app,
+            "/admin/v1/conversations",
+            json!({
+                "owner": {"type": "user", "user_id": user_id},
+                "title": "Attachments",
+                "messages": [
+                    {"role": "user", "content": "Look", "attachments": [{"file_id": missing_file}]}
+                ]
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, created) = post_json(
+            &app,
+            "/admin/v1/conversations",
+            json!({
+                "owner": {"type": "user", "user_id": user_id},
+                "title": "Attachments",
+                "messages": [
+                    {"role": "system", "content": "Be brief"},
+                    {"role": "user", "content": "Hello"},
+                    {"role": "assistant", "content": "Hi"}
+                ]
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let conv_id = created["id"].as_str().unwrap();
+
+        // Files not attached to the conversation are not served
+        let (status, _) = get_json(
+            &app,
+            &format!(
+                "/admin/v1/conversations/{}/attachments/{}",
+                conv_id, missing_file
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, replay) = get_json(
+            &app,
+            &format!(
+                "/admin/v1/conversations/{}/replay?model=test-openai/unknown-model",
+                conv_id
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(replay["vision"], false);
+        let messages = replay["messages"].as_array().unwrap();
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[0]["role"], "system");
+        assert_eq!(messages[1]["role"], "user");
+        assert_eq!(messages[1]["content"], "Hello");
+        assert_eq!(messages[2]["role"], "assistant");
+    }
+
+    #[tokio::test]
+    async fn test_append_messages() {
+        let app = test_app().await;
+        let user_id = create_user_with_id(&app, "append-msg-user").await;
+
+        let (status, created) = post_json(
+            &app,
+            "/admin/v1/conversations",
+            json!({
+                "owner": {"type": "user", "user_id": user_id},
+                "title": "Message Append Test",
+                "messages": [{"role": "user", "content": "First message"}]
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let conv_id = created["id"].as_str().unwrap();
+        let (status, body) = post_json(
+            &app,
+            &format!("/admin/v1/conversations/{}/messages", conv_id),
+            json!({
+                "messages": [
+                    {"role": "assistant", "content": "Second message"},
+                    {"role": "user", "content": "Third message"}
+                ]
+            }),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body.as_array().unwrap().len(), 3);
+        assert_eq!(body[0]["content"], "First message");
+        assert_eq!(body[1]["content"], "Second message");
+        assert_eq!(body[2]["content"], "Third message");
+    }
+
+    #[tokio::test]
+    async fn test_delete_conversation() {
+        let app = test_app().await;
+        let user_id = create_user_with_id(&app, "delete-conv-user").await;
+
+        let (status, created) = post_json(
             &app,
             "/admin/v1/conversations",
             json!({
@@ -1867,161 +2540,562 @@ api_key = "sk-test-key"
         );
     }
 
-    // ============================================================================
-    // Team Tests
-    // ============================================================================
-
     #[tokio::test]
-    async fn test_create_team() {
+    async fn test_get_concurrency_not_enabled() {
         let app = test_app().await;
-        let org_slug = create_org(&app, "team-test-org").await;
 
-        let (status, body) = post_json(
+        let (status, body) = get_json(&app, "/admin/v1/providers/nonexistent/concurrency").await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert!(
+            body["error"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("Adaptive concurrency is not enabled")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_provider_settings_lifecycle() {
+        let (app, state) = test_app_with_state().await;
+        let uri = "/admin/v1/providers/test-openai/settings";
+
+        let (status, body) = get_json(&app, uri).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["enabled"], true);
+        assert!(body["settings"].is_null());
+
+        let (status, body) = put_json(
             &app,
-            &format!("/admin/v1/organizations/{}/teams", org_slug),
-            json!({"slug": "engineering", "name": "Engineering Team"}),
+            uri,
+            json!({
+                "enabled": false,
+                "circuit_breaker": {"failure_threshold": 2}
+            }),
         )
         .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["enabled"], false);
+        assert_eq!(body["settings"]["enabled"], false);
+        assert_eq!(body["circuit_breaker"]["failure_threshold"], 2);
+        assert!(!state.provider_settings.is_enabled("test-openai"));
 
-        assert_eq!(status, StatusCode::CREATED);
-        assert_eq!(body["slug"], "engineering");
-        assert_eq!(body["name"], "Engineering Team");
-        assert!(body["id"].is_string());
+        let (status, body) = get_json(&app, uri).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["settings"]["circuit_breaker"]["failure_threshold"], 2);
+
+        let (status, body) = delete_json(&app, uri).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["enabled"], true);
+        assert!(body["settings"].is_null());
+        assert!(state.provider_settings.is_enabled("test-openai"));
     }
 
     #[tokio::test]
-    async fn test_create_team_duplicate_slug() {
+    async fn test_provider_settings_rejects_invalid() {
         let app = test_app().await;
-        let org_slug = create_org(&app, "dup-team-org").await;
 
-        let (status, _) = post_json(
+        // No fair queue is configured for the provider
+        let (status, _) = put_json(
             &app,
-            &format!("/admin/v1/organizations/{}/teams", org_slug),
-            json!({"slug": "duplicate-team", "name": "First Team"}),
+            "/admin/v1/providers/test-openai/settings",
+            json!({"max_concurrent": 4}),
         )
         .await;
-        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(status, StatusCode::BAD_REQUEST);
 
-        let (status, body) = post_json(
+        let (status, _) = put_json(
             &app,
-            &format!("/admin/v1/organizations/{}/teams", org_slug),
-            json!({"slug": "duplicate-team", "name": "Second Team"}),
+            "/admin/v1/providers/nonexistent/settings",
+            json!({"enabled": false}),
         )
         .await;
-
-        assert_eq!(status, StatusCode::CONFLICT);
-        assert!(body["error"]["code"].is_string());
+        assert_eq!(status, StatusCode::NOT_FOUND);
     }
 
     #[tokio::test]
-    async fn test_get_team() {
-        let app = test_app().await;
-        let org_slug = create_org(&app, "get-team-org").await;
-
-        let (status, created) = post_json(
-            &app,
-            &format!("/admin/v1/organizations/{}/teams", org_slug),
-            json!({"slug": "get-team", "name": "Get Team"}),
+    async fn test_bundle_export_import_roundtrip() {
+        let source = test_app().await;
+        let org_slug = create_org(&source, "bundle-org").await;
+        let team_id = create_team(&source, &org_slug, "platform").await;
+        let (status, _) = post_json(
+            &source,
+            "/admin/v1/organizations/bundle-org/projects",
+            json!({"slug": "api", "name": "API", "team_id": team_id}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let user_id = create_user_with_id(&source, "bundle-user").await;
+        let (status, _) = post_json(
+            &source,
+            "/admin/v1/organizations/bundle-org/teams/platform/members",
+            json!({"user_id": user_id, "role": "developer"}),
         )
         .await;
         assert_eq!(status, StatusCode::CREATED);
 
-        let (status, body) = get_json(
-            &app,
-            &format!("/admin/v1/organizations/{}/teams/get-team", org_slug),
+        let (status, bundle) = get_json(&source, "/admin/v1/export?orgs=bundle-org").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(bundle["version"], 1);
+        assert_eq!(bundle["organizations"][0]["slug"], "bundle-org");
+        assert_eq!(
+            bundle["organizations"][0]["projects"][0]["team"],
+            "platform"
+        );
+        assert_eq!(
+            bundle["organizations"][0]["teams"][0]["members"][0],
+            json!({"user": "bundle-user", "role": "developer"})
+        );
+        assert_eq!(bundle["users"][0]["external_id"], "bundle-user");
+
+        let target = test_app().await;
+        let (status, report) =
+            post_json(&target, "/admin/v1/import?dry_run=true", bundle.clone()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(report["dry_run"], true);
+        let changes = report["changes"].as_array().unwrap();
+        assert!(changes.iter().any(|c| c["resource"] == "team_member"
+            && c["key"] == "bundle-org/platform/bundle-user"
+            && c["action"] == "create"));
+        let (status, _) = get_json(&target, "/admin/v1/organizations/bundle-org").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, report) = post_json(&target, "/admin/v1/import", bundle.clone()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(report["dry_run"], false);
+        let (status, members) = get_json(
+            &target,
+            "/admin/v1/organizations/bundle-org/teams/platform/members",
         )
         .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(members["data"][0]["role"], "developer");
 
+        // Importing the same bundle again changes nothing
+        let (status, report) = post_json(&target, "/admin/v1/import", bundle).await;
         assert_eq!(status, StatusCode::OK);
-        assert_eq!(body["id"], created["id"]);
-        assert_eq!(body["slug"], "get-team");
+        let changes = report["changes"].as_array().unwrap();
+        assert!(!changes.is_empty());
+        assert!(changes.iter().all(|c| c["action"] == "unchanged"));
     }
 
     #[tokio::test]
-    async fn test_get_team_not_found() {
+    async fn test_bundle_import_rejects_invalid() {
         let app = test_app().await;
-        let org_slug = create_org(&app, "team-404-org").await;
 
-        let (status, body) = get_json(
+        let (status, body) = post_json(
             &app,
-            &format!("/admin/v1/organizations/{}/teams/nonexistent", org_slug),
+            "/admin/v1/import?dry_run=true",
+            json!({
+                "version": 1,
+                "organizations": [{
+                    "slug": "invalid-bundle",
+                    "name": "Invalid",
+                    "projects": [{"slug": "api", "name": "API", "team": "missing"}],
+                    "members": [{"user": "nobody", "role": "member"}]
+                }]
+            }),
         )
         .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let message = body["error"]["message"].as_str().unwrap();
+        assert!(message.contains("team 'missing'"));
+        assert!(message.contains("user 'nobody'"));
 
-        assert_eq!(status, StatusCode::NOT_FOUND);
-        assert!(body["error"]["code"].is_string());
+        let (status, _) = post_json(&app, "/admin/v1/import", json!({"version": 99})).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
     }
 
     #[tokio::test]
-    async fn test_list_teams() {
+    async fn test_bundle_export_yaml() {
         let app = test_app().await;
-        let org_slug = create_org(&app, "list-team-org").await;
+        create_org(&app, "yaml-org").await;
 
-        for i in 0..3 {
-            let (status, _) = post_json(
-                &app,
-                &format!("/admin/v1/organizations/{}/teams", org_slug),
-                json!({"slug": format!("team-{}", i), "name": format!("Team {}", i)}),
-            )
-            .await;
-            assert_eq!(status, StatusCode::CREATED);
-        }
+        let request = Request::builder()
+            .method("GET")
+            .uri("/admin/v1/export?format=yaml&orgs=yaml-org")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["content-type"], "application/yaml");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
 
-        let (status, body) =
-            get_json(&app, &format!("/admin/v1/organizations/{}/teams", org_slug)).await;
+        let request = Request::builder()
+            .method("POST")
+            .uri("/admin/v1/import?dry_run=true")
+            .header("content-type", "application/yaml")
+            .body(Body::from(body))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let report: Value = serde_json::from_slice(&body).unwrap();
+        let changes = report["changes"].as_array().unwrap();
+        assert!(changes.iter().any(|c| c["resource"] == "organization"
+            && c["key"] == "yaml-org"
+            && c["action"] == "unchanged"));
+    }
+
+    #[tokio::test]
+    async fn test_observability_grafana() {
+        let app = test_app().await;
+
+        let (status, body) = get_json(&app, "/admin/v1/observability/grafana").await;
 
         assert_eq!(status, StatusCode::OK);
-        assert_eq!(body["data"].as_array().unwrap().len(), 3);
-        assert_eq!(body["pagination"]["has_more"], false);
+        assert_eq!(body["dashboard"]["uid"], "hadrian-gateway");
+        assert!(body["dashboard"]["panels"].is_array());
+        assert!(body["alert_rules"]["groups"].is_array());
     }
 
     #[tokio::test]
-    async fn test_update_team() {
+    async fn test_slos_without_config() {
         let app = test_app().await;
-        let org_slug = create_org(&app, "update-team-org").await;
-
-        let (status, _) = post_json(
-            &app,
-            &format!("/admin/v1/organizations/{}/teams", org_slug),
-            json!({"slug": "update-team", "name": "Original Name"}),
-        )
-        .await;
-        assert_eq!(status, StatusCode::CREATED);
 
-        let (status, body) = patch_json(
-            &app,
-            &format!("/admin/v1/organizations/{}/teams/update-team", org_slug),
-            json!({"name": "Updated Name"}),
-        )
-        .await;
+        let (status, body) = get_json(&app, "/admin/v1/slos").await;
 
         assert_eq!(status, StatusCode::OK);
-        assert_eq!(body["name"], "Updated Name");
+        assert_eq!(body["slos"], json!([]));
     }
 
     #[tokio::test]
-    async fn test_delete_team() {
+    async fn test_system_drain() {
         let app = test_app().await;
-        let org_slug = create_org(&app, "delete-team-org").await;
 
-        let (status, _) = post_json(
-            &app,
-            &format!("/admin/v1/organizations/{}/teams", org_slug),
-            json!({"slug": "delete-team", "name": "To Be Deleted"}),
-        )
-        .await;
-        assert_eq!(status, StatusCode::CREATED);
+        let (status, body) = get_json(&app, "/admin/v1/system/drain").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["draining"], false);
+        assert_eq!(body["in_flight"], 0);
+        let (status, _) = get_json(&app, "/health/ready").await;
+        assert_eq!(status, StatusCode::OK);
 
-        let (status, _) = delete_json(
-            &app,
-            &format!("/admin/v1/organizations/{}/teams/delete-team", org_slug),
-        )
-        .await;
+        let (status, body) = post_json(&app, "/admin/v1/system/drain", json!({})).await;
         assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["draining"], true);
+        assert!(body["started_at"].is_string());
 
-        let (status, _) = get_json(
-            &app,
-            &format!("/admin/v1/organizations/{}/teams/delete-team", org_slug),
+        // Readiness fails and new data-plane requests are turned away
+        let (status, _) = get_json(&app, "/health/ready").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/models")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.headers()["retry-after"], "5");
+
+        // Admin endpoints keep working and a second drain keeps the first start
+        let (status, again) = post_json(&app, "/admin/v1/system/drain", json!({})).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(again["started_at"], body["started_at"]);
+    }
+
+    #[tokio::test]
+    async fn test_system_jobs() {
+        let app = test_app().await;
+
+        let (status, body) = get_json(&app, "/admin/v1/system/jobs").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["node_id"], crate::jobs::leader_lock::node_id());
+        assert!(body["jobs"].as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_jobs_api() {
+        let (app, state) = test_app_with_state().await;
+        let job = state
+            .job_scheduler
+            .register("retention", std::time::Duration::from_secs(3600));
+
+        let (status, body) = get_json(&app, "/admin/v1/jobs").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"].as_array().unwrap().len(), 1);
+        assert_eq!(body["data"][0]["name"], "retention");
+        assert_eq!(body["data"][0]["interval_secs"], 3600);
+        assert_eq!(body["data"][0]["paused"], false);
+
+        let (status, _) = get_json(&app, "/admin/v1/jobs/unknown").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, _) = post_json(&app, "/admin/v1/jobs/unknown/run", json!({})).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        // Pausing is stored in the database and skips scheduled runs only
+        let (status, body) = post_json(&app, "/admin/v1/jobs/retention/pause", json!({})).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["paused"], true);
+        assert!(!job.should_run(crate::models::JobTrigger::Scheduled).await);
+
+        // A manual trigger wakes the worker, which records the run
+        let (status, _) = post_json(&app, "/admin/v1/jobs/retention/run", json!({})).await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        let trigger = job.tick().await;
+        assert_eq!(trigger, crate::models::JobTrigger::Manual);
+        assert!(job.should_run(trigger).await);
+        job.run(trigger, async { Ok::<_, String>(4) }, |n| {
+            Some(format!("{n} records deleted"))
+        })
+        .await
+        .unwrap();
+
+        let (status, body) = get_json(&app, "/admin/v1/jobs/retention/runs").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"].as_array().unwrap().len(), 1);
+        assert_eq!(body["data"][0]["triggered_by"], "manual");
+        assert_eq!(body["data"][0]["status"], "succeeded");
+        assert_eq!(body["data"][0]["summary"], "4 records deleted");
+
+        let (status, body) = post_json(&app, "/admin/v1/jobs/retention/resume", json!({})).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["paused"], false);
+        assert_eq!(body["last_run"]["status"], "succeeded");
+    }
+
+    #[tokio::test]
+    async fn test_federation_report_and_summary() {
+        const TOKEN: &str = "eu-west-1-federation-token-0123456789";
+        let config_str = format!(
+            r#"
+{}
+
+[[federation.hub.satellites]]
+id = "eu-west-1"
+token = "{TOKEN}"
+"#,
+            unique_db_config()
+        );
+        let app = test_app_with_config(&config_str).await;
+
+        let report = |gateway_id: &str| {
+            json!({
+                "gateway_id": gateway_id,
+                "region": "eu-west",
+                "version": "1.0.0",
+                "generated_at": "2025-03-02T12:00:00Z",
+                "usage": [
+                    {"date": "2025-03-01", "model": "gpt-4o", "total_cost_microcents": 500,
+                     "input_tokens": 10, "output_tokens": 20, "total_tokens": 30, "request_count": 2},
+                    {"date": "2025-03-02", "model": "gpt-4o", "total_cost_microcents": 250,
+                     "input_tokens": 5, "output_tokens": 10, "total_tokens": 15, "request_count": 1}
+                ],
+                "providers": [{"provider": "openai", "status": "healthy", "circuit_state": "closed"}]
+            })
+        };
+        let send = |token: &'static str, body: Value| {
+            let app = app.clone();
+            async move {
+                let request = Request::builder()
+                    .method("POST")
+                    .uri("/federation/v1/reports")
+                    .header("content-type", "application/json")
+                    .header("authorization", format!("Bearer {token}"))
+                    .body(Body::from(body.to_string()))
+                    .unwrap();
+                app.oneshot(request).await.unwrap().status()
+            }
+        };
+
+        assert_eq!(
+            send("wrong-token", report("eu-west-1")).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            send(TOKEN, report("us-east-1")).await,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            send(TOKEN, report("eu-west-1")).await,
+            StatusCode::NO_CONTENT
+        );
+
+        let (status, body) = get_json(&app, "/admin/v1/federation/gateways").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body.as_array().unwrap().len(), 1);
+        assert_eq!(body[0]["id"], "eu-west-1");
+        assert_eq!(body[0]["providers"][0]["circuit_state"], "closed");
+
+        let (status, body) = get_json(
+            &app,
+            "/admin/v1/federation/summary?start_date=2025-03-01&end_date=2025-03-02",
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["totals"]["total_cost_microcents"], 750);
+        assert_eq!(body["totals"]["request_count"], 3);
+        assert_eq!(body["by_gateway"][0]["gateway_id"], "eu-west-1");
+        assert_eq!(body["by_date"].as_array().unwrap().len(), 2);
+
+        let (status, _) = delete_json(&app, "/admin/v1/federation/gateways/eu-west-1").await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, _) = get_json(&app, "/admin/v1/federation/gateways/eu-west-1").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    // ============================================================================
+    // Team Tests
+    // ============================================================================
+
+    #[tokio::test]
+    async fn test_create_team() {
+        let app = test_app().await;
+        let org_slug = create_org(&app, "team-test-org").await;
+
+        let (status, body) = post_json(
+            &app,
+            &format!("/admin/v1/organizations/{}/teams", org_slug),
+            json!({"slug": "engineering", "name": "Engineering Team"}),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(body["slug"], "engineering");
+        assert_eq!(body["name"], "Engineering Team");
+        assert!(body["id"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_create_team_duplicate_slug() {
+        let app = test_app().await;
+        let org_slug = create_org(&app, "dup-team-org").await;
+
+        let (status, _) = post_json(
+            &app,
+            &format!("/admin/v1/organizations/{}/teams", org_slug),
+            json!({"slug": "duplicate-team", "name": "First Team"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let (status, body) = post_json(
+            &app,
+            &format!("/admin/v1/organizations/{}/teams", org_slug),
+            json!({"slug": "duplicate-team", "name": "Second Team"}),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert!(body["error"]["code"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_get_team() {
+        let app = test_app().await;
+        let org_slug = create_org(&app, "get-team-org").await;
+
+        let (status, created) = post_json(
+            &app,
+            &format!("/admin/v1/organizations/{}/teams", org_slug),
+            json!({"slug": "get-team", "name": "Get Team"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let (status, body) = get_json(
+            &app,
+            &format!("/admin/v1/organizations/{}/teams/get-team", org_slug),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["id"], created["id"]);
+        assert_eq!(body["slug"], "get-team");
+    }
+
+    #[tokio::test]
+    async fn test_get_team_not_found() {
+        let app = test_app().await;
+        let org_slug = create_org(&app, "team-404-org").await;
+
+        let (status, body) = get_json(
+            &app,
+            &format!("/admin/v1/organizations/{}/teams/nonexistent", org_slug),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert!(body["error"]["code"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_list_teams() {
+        let app = test_app().await;
+        let org_slug = create_org(&app, "list-team-org").await;
+
+        for i in 0..3 {
+            let (status, _) = post_json(
+                &app,
+                &format!("/admin/v1/organizations/{}/teams", org_slug),
+                json!({"slug": format!("team-{}", i), "name": format!("Team {}", i)}),
+            )
+            .await;
+            assert_eq!(status, StatusCode::CREATED);
+        }
+
+        let (status, body) =
+            get_json(&app, &format!("/admin/v1/organizations/{}/teams", org_slug)).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"].as_array().unwrap().len(), 3);
+        assert_eq!(body["pagination"]["has_more"], false);
+    }
+
+    #[tokio::test]
+    async fn test_update_team() {
+        let app = test_app().await;
+        let org_slug = create_org(&app, "update-team-org").await;
+
+        let (status, _) = post_json(
+            &app,
+            &format!("/admin/v1/organizations/{}/teams", org_slug),
+            json!({"slug": "update-team", "name": "Original Name"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let (status, body) = patch_json(
+            &app,
+            &format!("/admin/v1/organizations/{}/teams/update-team", org_slug),
+            json!({"name": "Updated Name"}),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["name"], "Updated Name");
+    }
+
+    #[tokio::test]
+    async fn test_delete_team() {
+        let app = test_app().await;
+        let org_slug = create_org(&app, "delete-team-org").await;
+
+        let (status, _) = post_json(
+            &app,
+            &format!("/admin/v1/organizations/{}/teams", org_slug),
+            json!({"slug": "delete-team", "name": "To Be Deleted"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let (status, _) = delete_json(
+            &app,
+            &format!("/admin/v1/organizations/{}/teams/delete-team", org_slug),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, _) = get_json(
+            &app,
+            &format!("/admin/v1/organizations/{}/teams/delete-team", org_slug),
         )
         .await;
         assert_eq!(status, StatusCode::NOT_FOUND);
@@ -2756,48 +3830,842 @@ api_key = "sk-test-key"
     }
 
     #[tokio::test]
-    async fn test_get_provider_usage_by_date_empty() {
+    async fn test_get_provider_usage_by_date_empty() {
+        let app = test_app().await;
+
+        let (status, body) = get_json(&app, "/admin/v1/providers/anthropic/usage/by-date").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_provider_usage_by_model_empty() {
+        let app = test_app().await;
+
+        let (status, body) = get_json(&app, "/admin/v1/providers/openai/usage/by-model").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_provider_usage_forecast() {
+        let app = test_app().await;
+
+        let (status, body) = get_json(&app, "/admin/v1/providers/openai/usage/forecast").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["current_spend"], 0.0);
+        assert_eq!(body["avg_daily_spend"], 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_get_provider_usage_forecast_with_params() {
+        let app = test_app().await;
+
+        let (status, body) = get_json(
+            &app,
+            "/admin/v1/providers/anthropic/usage/forecast?lookback_days=14&forecast_days=7",
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["current_spend"], 0.0);
+    }
+
+    // ============================================================================
+    // Model Catalog Tests
+    // ============================================================================
+
+    #[tokio::test]
+    async fn test_model_catalog_entry_lifecycle() {
+        let app = test_app().await;
+
+        let (status, created) = post_json(
+            &app,
+            "/admin/v1/model-catalog",
+            json!({
+                "provider": "vllm",
+                "model": "llama-3.1-8b-ft",
+                "capabilities": {"tool_call": true},
+                "limits": {"context_length": 131072},
+                "pricing": {"input": 0.1, "output": 0.2}
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(created["provider"], "vllm");
+        assert_eq!(created["capabilities"]["tool_call"], true);
+        assert_eq!(created["pricing"]["output"], 0.2);
+        let id = created["id"].as_str().unwrap();
+
+        let (status, _) = post_json(
+            &app,
+            "/admin/v1/model-catalog",
+            json!({"provider": "vllm", "model": "llama-3.1-8b-ft"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        let (status, updated) = put_json(
+            &app,
+            &format!("/admin/v1/model-catalog/{}", id),
+            json!({"family": "llama"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(updated["family"], "llama");
+        assert!(updated["pricing"].is_null());
+
+        let (status, list) = get_json(&app, "/admin/v1/model-catalog").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(list.as_array().unwrap().len(), 1);
+
+        let (status, _) = delete_json(&app, &format!("/admin/v1/model-catalog/{}", id)).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, _) = get_json(&app, &format!("/admin/v1/model-catalog/{}", id)).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_create_model_catalog_entry_rejects_negative_pricing() {
+        let app = test_app().await;
+
+        let (status, _) = post_json(
+            &app,
+            "/admin/v1/model-catalog",
+            json!({
+                "provider": "vllm",
+                "model": "llama-3.1-8b-ft",
+                "pricing": {"input": -1.0, "output": 0.2}
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    // ============================================================================
+    // Agent Tests
+    // ============================================================================
+
+    #[cfg(feature = "server")]
+    #[tokio::test]
+    async fn test_agent_lifecycle() {
+        let app = test_app().await;
+        let org_slug = create_org(&app, "agent-org").await;
+        let agents_uri = format!("/admin/v1/organizations/{}/agents", org_slug);
+
+        let (status, created) = post_json(
+            &app,
+            &agents_uri,
+            json!({
+                "name": "support",
+                "model": "openai/gpt-4o",
+                "instructions": "Answer from the knowledge base.",
+                "max_iterations": 4
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(created["name"], "support");
+        assert_eq!(created["max_iterations"], 4);
+        let id = created["id"].as_str().unwrap();
+
+        let (status, _) = post_json(
+            &app,
+            &agents_uri,
+            json!({"name": "support", "model": "openai/gpt-4o"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        // Only tools the gateway executes itself are accepted
+        let (status, _) = post_json(
+            &app,
+            &agents_uri,
+            json!({
+                "name": "orders",
+                "model": "openai/gpt-4o",
+                "tools": [{"type": "function", "name": "lookup_order"}]
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, updated) = patch_json(
+            &app,
+            &format!("{}/{}", agents_uri, id),
+            json!({"instructions": "Be brief."}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(updated["instructions"], "Be brief.");
+        assert_eq!(updated["max_iterations"], 4);
+
+        // Agents aren't visible through other organizations
+        let other_slug = create_org(&app, "other-agent-org").await;
+        let (status, _) = get_json(
+            &app,
+            &format!("/admin/v1/organizations/{}/agents/{}", other_slug, id),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, list) = get_json(&app, &agents_uri).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(list["data"].as_array().unwrap().len(), 1);
+
+        let (status, _) = delete_json(&app, &format!("{}/{}", agents_uri, id)).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, _) = get_json(&app, &format!("{}/{}", agents_uri, id)).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    // ============================================================================
+    // Scheduled Task Tests
+    // ============================================================================
+
+    #[tokio::test]
+    async fn test_scheduled_task_lifecycle() {
+        let config_str = format!(
+            r#"
+{}
+
+[features.scheduled_tasks]
+enabled = true
+min_interval_secs = 3600
+
+[limits.resource_limits]
+max_scheduled_tasks_per_org = 1
+"#,
+            unique_db_config()
+        );
+        let app = test_app_with_config(&config_str).await;
+        let org_slug = create_org(&app, "scheduled-task-org").await;
+        let tasks_uri = format!("/admin/v1/organizations/{}/scheduled-tasks", org_slug);
+        let task = |schedule: &str, url: &str| {
+            json!({
+                "name": "daily-briefing",
+                "schedule": schedule,
+                "model": "test-openai/gpt-4o-mini",
+                "prompt": "Write today's briefing.",
+                "delivery": {"type": "webhook", "url": url, "signing_secret": "whsec_test"}
+            })
+        };
+
+        // Schedules running more often than min_interval_secs are rejected
+        let (status, _) = post_json(
+            &app,
+            &tasks_uri,
+            task("*/5 * * * *", "https://93.184.216.34/hook"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) =
+            post_json(&app, &tasks_uri, task("0 8 * * *", "http://127.0.0.1/hook")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = post_json(
+            &app,
+            &tasks_uri,
+            json!({
+                "name": "to-conversation",
+                "schedule": "@daily",
+                "model": "test-openai/gpt-4o-mini",
+                "prompt": "Summarize.",
+                "delivery": {"type": "conversation", "conversation_id": uuid::Uuid::new_v4()}
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, created) = post_json(
+            &app,
+            &tasks_uri,
+            task("0 8 * * 1-5", "https://93.184.216.34/hook"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(created["enabled"], true);
+        assert!(created["next_run_at"].is_string());
+        assert!(created["delivery"].get("signing_secret").is_none());
+        let id = created["id"].as_str().unwrap();
+
+        // The organization is at its task limit
+        let mut second = task("0 9 * * *", "https://93.184.216.34/hook");
+        second["name"] = json!("second");
+        let (status, _) = post_json(&app, &tasks_uri, second).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        let (status, updated) = patch_json(
+            &app,
+            &format!("{}/{}", tasks_uri, id),
+            json!({"enabled": false}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(updated["enabled"], false);
+        assert!(updated.get("next_run_at").is_none());
+
+        let (status, runs) = get_json(&app, &format!("{}/{}/runs", tasks_uri, id)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(runs["data"].as_array().unwrap().is_empty());
+
+        // Tasks aren't visible through other organizations
+        let other_slug = create_org(&app, "other-scheduled-task-org").await;
+        let (status, _) = get_json(
+            &app,
+            &format!(
+                "/admin/v1/organizations/{}/scheduled-tasks/{}",
+                other_slug, id
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, _) = delete_json(&app, &format!("{}/{}", tasks_uri, id)).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, list) = get_json(&app, &tasks_uri).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(list["data"].as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_scheduled_tasks_require_feature() {
+        let app = test_app().await;
+        let org_slug = create_org(&app, "no-scheduled-tasks-org").await;
+        let (status, _) = get_json(
+            &app,
+            &format!("/admin/v1/organizations/{}/scheduled-tasks", org_slug),
+        )
+        .await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    // ============================================================================
+    // Invitation Tests
+    // ============================================================================
+
+    #[cfg(feature = "sso")]
+    fn invitations_config() -> String {
+        format!(
+            r#"
+{}
+
+[features.invitations]
+enabled = true
+ttl_secs = 3600
+"#,
+            unique_db_config()
+        )
+    }
+
+    #[cfg(feature = "sso")]
+    #[tokio::test]
+    async fn test_invitation_lifecycle() {
+        let app = test_app_with_config(&invitations_config()).await;
+        let org_slug = create_org(&app, "invite-org").await;
+        create_team(&app, &org_slug, "platform").await;
+        let invitations_uri = format!("/admin/v1/organizations/{}/invitations", org_slug);
+
+        let (status, created) = post_json(
+            &app,
+            &invitations_uri,
+            json!({"email": "Alice@Example.com", "role": "admin"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(created["email"], "alice@example.com");
+        assert_eq!(created["status"], "pending");
+        assert_eq!(created["delivered"], false);
+        let first_url = created["invite_url"].as_str().unwrap().to_string();
+        assert!(first_url.starts_with("/auth/invitations/accept?token="));
+        let id = created["id"].as_str().unwrap();
+
+        // One pending invitation per address, organization and team
+        let (status, _) = post_json(
+            &app,
+            &invitations_uri,
+            json!({"email": "alice@example.com"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        let (status, _) = post_json(
+            &app,
+            &invitations_uri,
+            json!({"email": "alice@example.com", "team_slug": "missing"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, team_invite) = post_json(
+            &app,
+            &invitations_uri,
+            json!({"email": "alice@example.com", "team_slug": "platform"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert!(team_invite["team_id"].is_string());
+
+        let (status, list) = get_json(&app, &invitations_uri).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(list["data"].as_array().unwrap().len(), 2);
+
+        // Resending issues a new link
+        let (status, resent) = post_json(
+            &app,
+            &format!("{}/{}/resend", invitations_uri, id),
+            json!({}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_ne!(resent["invite_url"], first_url.as_str());
+
+        let (status, revoked) = delete_json(&app, &format!("{}/{}", invitations_uri, id)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(revoked["status"], "revoked");
+        let (status, _) = delete_json(&app, &format!("{}/{}", invitations_uri, id)).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        let (status, _) = post_json(
+            &app,
+            &format!("{}/{}/resend", invitations_uri, id),
+            json!({}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        // Invitations aren't visible through other organizations
+        let other_slug = create_org(&app, "other-invite-org").await;
+        let (status, _) = get_json(
+            &app,
+            &format!("/admin/v1/organizations/{}/invitations/{}", other_slug, id),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[cfg(feature = "sso")]
+    #[tokio::test]
+    async fn test_invitation_accepted_on_sign_in() {
+        use crate::services::invitations::SignInIdentity;
+
+        let config = crate::config::GatewayConfig::parse(&format!(
+            r#"
+{}
+
+[auth.session]
+secret = "test-session-secret-must-be-long-enough-for-hmac-pepper-32b"
+"#,
+            invitations_config()
+        ))
+        .expect("Failed to parse test config");
+        let state = crate::AppState::new(config.clone())
+            .await
+            .expect("Failed to create AppState");
+        let app = crate::build_app(&config, state.clone());
+        let invitations = &state.services.as_ref().unwrap().invitations;
+
+        let org_slug = create_org(&app, "sign-in-org").await;
+        create_team(&app, &org_slug, "research").await;
+        let (_, org) = get_json(&app, &format!("/admin/v1/organizations/{}", org_slug)).await;
+        let org_id: uuid::Uuid = org["id"].as_str().unwrap().parse().unwrap();
+        let invitations_uri = format!("/admin/v1/organizations/{}/invitations", org_slug);
+
+        let (_, team_invite) = post_json(
+            &app,
+            &invitations_uri,
+            json!({"email": "alice@example.com", "team_slug": "research", "role": "admin"}),
+        )
+        .await;
+        let (_, link_invite) =
+            post_json(&app, &invitations_uri, json!({"email": "bob@example.com"})).await;
+        let token = link_invite["invite_url"]
+            .as_str()
+            .unwrap()
+            .split("token=")
+            .nth(1)
+            .unwrap()
+            .to_string();
+
+        // Signing in through the organization's IdP accepts pending
+        // invitations for the address
+        let accepted = invitations
+            .accept_on_sign_in(
+                SignInIdentity {
+                    external_id: "alice-sub",
+                    email: Some("Alice@example.com"),
+                    name: Some("Alice"),
+                    sso_org_id: Some(org_id),
+                },
+                None,
+            )
+            .await
+            .expect("accept invitations");
+        assert_eq!(accepted.len(), 1);
+        assert_eq!(
+            accepted[0].id.to_string(),
+            team_invite["id"].as_str().unwrap()
+        );
+
+        let (_, members) = get_json(
+            &app,
+            &format!(
+                "/admin/v1/organizations/{}/teams/research/members",
+                org_slug
+            ),
+        )
+        .await;
+        let members = members["data"].as_array().unwrap();
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0]["role"], "admin");
+
+        // A link only works for the address it was sent to
+        let accepted = invitations
+            .accept_on_sign_in(
+                SignInIdentity {
+                    external_id: "carol-sub",
+                    email: Some("carol@example.com"),
+                    name: None,
+                    sso_org_id: None,
+                },
+                Some(&token),
+            )
+            .await
+            .unwrap();
+        assert!(accepted.is_empty());
+
+        // Without the organization's IdP vouching for the address, the link
+        // is needed
+        let bob = SignInIdentity {
+            external_id: "bob-sub",
+            email: Some("bob@example.com"),
+            name: None,
+            sso_org_id: None,
+        };
+        assert!(
+            invitations
+                .accept_on_sign_in(bob, None)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        let accepted = invitations
+            .accept_on_sign_in(bob, Some(&token))
+            .await
+            .unwrap();
+        assert_eq!(accepted.len(), 1);
+
+        let (_, members) = get_json(
+            &app,
+            &format!("/admin/v1/organizations/{}/members", org_slug),
+        )
+        .await;
+        assert_eq!(members["data"].as_array().unwrap().len(), 2);
+
+        let (_, list) = get_json(&app, &invitations_uri).await;
+        assert!(
+            list["data"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .all(|i| i["status"] == "accepted")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invitations_require_feature() {
+        let app = test_app().await;
+        let org_slug = create_org(&app, "no-invitations-org").await;
+        let (status, _) = get_json(
+            &app,
+            &format!("/admin/v1/organizations/{}/invitations", org_slug),
+        )
+        .await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    // ============================================================================
+    // Role Elevation Tests
+    // ============================================================================
+
+    fn elevations_config() -> String {
+        format!(
+            r#"
+{}
+
+[features.elevations]
+enabled = true
+roles = ["admin"]
+max_duration_hours = 4
+"#,
+            unique_db_config()
+        )
+    }
+
+    #[tokio::test]
+    async fn test_role_elevation_lifecycle() {
+        let (app, state) = test_app_with_config_and_state(&elevations_config()).await;
+        let elevations = &state.services.as_ref().unwrap().elevations;
+        let (_, org) = get_json(&app, "/admin/v1/organizations/local").await;
+        let org_id: uuid::Uuid = org["id"].as_str().unwrap().parse().unwrap();
+        let elevations_uri = "/admin/v1/organizations/local/elevations";
+
+        // The anonymous user is a member of the "local" organization
+        let (_, me) = get_json(&app, "/admin/v1/me/export").await;
+        let me_id = me["user"]["id"].as_str().unwrap().to_string();
+
+        let (status, own) = post_json(
+            &app,
+            elevations_uri,
+            json!({"role": "admin", "justification": "Incident 7", "duration_hours": 2}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(own["status"], "pending");
+        assert_eq!(own["user_id"], me_id.as_str());
+        let own_id = own["id"].as_str().unwrap();
+
+        // Members can't approve their own elevation, but can withdraw it
+        let (status, _) = post_json(
+            &app,
+            &format!("{}/{}/approve", elevations_uri, own_id),
+            json!({}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        let (status, withdrawn) = post_json(
+            &app,
+            &format!("{}/{}/revoke", elevations_uri, own_id),
+            json!({}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(withdrawn["status"], "revoked");
+
+        let (_, user) = post_json(
+            &app,
+            "/admin/v1/users",
+            json!({"external_id": "oncall", "email": "oncall@example.com", "name": "On-call"}),
+        )
+        .await;
+        let user_id = user["id"].as_str().unwrap();
+        let (status, _) = post_json(
+            &app,
+            "/admin/v1/organizations/local/members",
+            json!({"user_id": user_id, "role": "member"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let user_id: uuid::Uuid = user_id.parse().unwrap();
+
+        // Only configured roles, up to max_duration_hours
+        for body in [
+            json!({"user_id": user_id, "role": "owner", "justification": "x", "duration_hours": 1}),
+            json!({"user_id": user_id, "role": "admin", "justification": "x", "duration_hours": 5}),
+            json!({"user_id": user_id, "role": "member", "justification": "x", "duration_hours": 1}),
+        ] {
+            let (status, _) = post_json(&app, elevations_uri, body).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+        }
+
+        let request = json!({
+            "user_id": user_id,
+            "role": "admin",
+            "justification": "Rotate leaked provider key",
+            "duration_hours": 2,
+        });
+        let (status, created) = post_json(&app, elevations_uri, request.clone()).await;
+        assert_eq!(status, StatusCode::CREATED);
+        let id = created["id"].as_str().unwrap();
+        let (status, _) = post_json(&app, elevations_uri, request.clone()).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        let (status, approved) = post_json(
+            &app,
+            &format!("{}/{}/approve", elevations_uri, id),
+            json!({"note": "ok"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(approved["status"], "active");
+        assert_eq!(approved["previous_role"], "member");
+        assert!(approved["expires_at"].is_string());
+        assert_eq!(
+            elevations
+                .current_role(org_id, user_id)
+                .await
+                .unwrap()
+                .as_deref(),
+            Some("admin")
+        );
+        let (status, _) =
+            post_json(&app, &format!("{}/{}/deny", elevations_uri, id), json!({})).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        // Ending early restores the previous role
+        let (status, revoked) = post_json(
+            &app,
+            &format!("{}/{}/revoke", elevations_uri, id),
+            json!({}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(revoked["status"], "revoked");
+        assert_eq!(
+            elevations
+                .current_role(org_id, user_id)
+                .await
+                .unwrap()
+                .as_deref(),
+            Some("member")
+        );
+
+        // Expiry restores it too
+        let (_, created) = post_json(&app, elevations_uri, request).await;
+        let id = created["id"].as_str().unwrap();
+        let (status, _) = post_json(
+            &app,
+            &format!("{}/{}/approve", elevations_uri, id),
+            json!({}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let now = chrono::Utc::now();
+        assert_eq!(elevations.expire_due(now).await.unwrap(), 0);
+        assert_eq!(
+            elevations
+                .expire_due(now + chrono::Duration::hours(3))
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            elevations
+                .current_role(org_id, user_id)
+                .await
+                .unwrap()
+                .as_deref(),
+            Some("member")
+        );
+        let (_, expired) = get_json(&app, &format!("{}/{}", elevations_uri, id)).await;
+        assert_eq!(expired["status"], "expired");
+
+        let (status, list) = get_json(&app, elevations_uri).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(list["data"].as_array().unwrap().len(), 3);
+
+        let (_, logs) = get_json(
+            &app,
+            "/admin/v1/audit-logs?resource_type=role_elevation&limit=100",
+        )
+        .await;
+        let actions: Vec<&str> = logs["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|l| l["action"].as_str().unwrap())
+            .collect();
+        for action in [
+            "elevation.request",
+            "elevation.approve",
+            "elevation.revoke",
+            "elevation.expire",
+        ] {
+            assert!(actions.contains(&action), "missing {action}: {actions:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_role_elevations_require_feature() {
         let app = test_app().await;
-
-        let (status, body) = get_json(&app, "/admin/v1/providers/anthropic/usage/by-date").await;
-
-        assert_eq!(status, StatusCode::OK);
-        assert!(body.as_array().unwrap().is_empty());
+        let (status, _) = get_json(&app, "/admin/v1/organizations/local/elevations").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
     }
 
+    // ============================================================================
+    // Provenance Tests
+    // ============================================================================
+
     #[tokio::test]
-    async fn test_get_provider_usage_by_model_empty() {
-        let app = test_app().await;
+    async fn test_provenance_verify() {
+        use crate::services::provenance::ProvenanceMetadata;
 
-        let (status, body) = get_json(&app, "/admin/v1/providers/openai/usage/by-model").await;
+        let key = "0123456789abcdef0123456789abcdef";
+        let config_str = format!(
+            r#"
+{}
+
+[features.provenance]
+enabled = true
+signing_key = "{key}"
+"#,
+            unique_db_config()
+        );
+        let app = test_app_with_config(&config_str).await;
+        let metadata = ProvenanceMetadata::sign(
+            key,
+            "req-123",
+            "test-openai/gpt-4o-mini",
+            "The answer is 42.",
+            chrono::Utc::now(),
+        );
 
+        let (status, body) = post_json(
+            &app,
+            "/admin/v1/provenance/verify",
+            json!({"text": "The answer is 42.", "metadata": metadata}),
+        )
+        .await;
         assert_eq!(status, StatusCode::OK);
-        assert!(body.as_array().unwrap().is_empty());
-    }
+        assert_eq!(body["valid"], true);
+        assert_eq!(body["request_id"], "req-123");
+        assert_eq!(body["model"], "test-openai/gpt-4o-mini");
 
-    #[tokio::test]
-    async fn test_get_provider_usage_forecast() {
-        let app = test_app().await;
+        // The header form is accepted too
+        let (status, body) = post_json(
+            &app,
+            "/admin/v1/provenance/verify",
+            json!({"text": "The answer is 43.", "metadata": metadata.to_header()}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["valid"], false);
+        assert_eq!(body["reason"], "content_mismatch");
+        assert!(body.get("request_id").is_none());
 
-        let (status, body) = get_json(&app, "/admin/v1/providers/openai/usage/forecast").await;
+        let forged = ProvenanceMetadata::sign(
+            "fedcba9876543210fedcba9876543210",
+            "req-123",
+            "test-openai/gpt-4o-mini",
+            "The answer is 42.",
+            chrono::Utc::now(),
+        );
+        let (_, body) = post_json(
+            &app,
+            "/admin/v1/provenance/verify",
+            json!({"text": "The answer is 42.", "metadata": forged}),
+        )
+        .await;
+        assert_eq!(body["reason"], "invalid_signature");
 
-        assert_eq!(status, StatusCode::OK);
-        assert_eq!(body["current_spend"], 0.0);
-        assert_eq!(body["avg_daily_spend"], 0.0);
+        let (status, _) = post_json(
+            &app,
+            "/admin/v1/provenance/verify",
+            json!({"text": "The answer is 42.", "metadata": "not-a-header!"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
     }
 
     #[tokio::test]
-    async fn test_get_provider_usage_forecast_with_params() {
+    async fn test_provenance_verify_requires_feature() {
         let app = test_app().await;
-
-        let (status, body) = get_json(
+        let (status, _) = post_json(
             &app,
-            "/admin/v1/providers/anthropic/usage/forecast?lookback_days=14&forecast_days=7",
+            "/admin/v1/provenance/verify",
+            json!({"text": "Hello", "metadata": "e30"}),
         )
         .await;
-
-        assert_eq!(status, StatusCode::OK);
-        assert_eq!(body["current_spend"], 0.0);
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
     }
 
     // ============================================================================
@@ -3518,112 +5386,436 @@ api_key = "sk-test-key"
     }
 
     #[tokio::test]
-    async fn test_list_audit_logs_invalid_direction() {
+    async fn test_list_audit_logs_invalid_direction() {
+        let app = test_app().await;
+
+        // Invalid direction should return 400
+        let (status, body) = get_json(&app, "/admin/v1/audit-logs?direction=invalid").await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(
+            body["error"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("Invalid direction")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_audit_log_by_id() {
+        let app = test_app().await;
+
+        // Create an organization to generate an audit log
+        let (status, _) = post_json(
+            &app,
+            "/admin/v1/organizations",
+            json!({"slug": "get-audit-org", "name": "Get Audit Org"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+
+        // List audit logs to get an ID
+        let (_, list_body) = get_json(&app, "/admin/v1/audit-logs").await;
+        let logs = list_body["data"].as_array().unwrap();
+        assert!(!logs.is_empty());
+
+        let log_id = logs[0]["id"].as_str().unwrap();
+
+        // Get the specific audit log by ID
+        let (status, body) = get_json(&app, &format!("/admin/v1/audit-logs/{}", log_id)).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["id"], log_id);
+        assert!(body["action"].is_string());
+        assert!(body["resource_type"].is_string());
+        assert!(body["timestamp"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_get_audit_log_not_found() {
+        let app = test_app().await;
+
+        let fake_id = "00000000-0000-0000-0000-000000000000";
+        let (status, body) = get_json(&app, &format!("/admin/v1/audit-logs/{}", fake_id)).await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert!(
+            body["error"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("not found")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_audit_log_invalid_id() {
+        let app = test_app().await;
+
+        let (status, _) = get_json(&app, "/admin/v1/audit-logs/not-a-uuid").await;
+
+        // Invalid UUID format should return 400
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_list_audit_logs_multiple_actions() {
+        let app = test_app().await;
+
+        // Create organization
+        let (status, org) = post_json(
+            &app,
+            "/admin/v1/organizations",
+            json!({"slug": "multi-action-org", "name": "Multi Action Org"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let org_slug = org["slug"].as_str().unwrap();
+
+        // Update organization (generates organization.update audit log)
+        let (status, _) = patch_json(
+            &app,
+            &format!("/admin/v1/organizations/{}", org_slug),
+            json!({"name": "Updated Multi Action Org"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        // List all audit logs for this org
+        let org_id = org["id"].as_str().unwrap();
+        let (status, body) =
+            get_json(&app, &format!("/admin/v1/audit-logs?org_id={}", org_id)).await;
+
+        assert_eq!(status, StatusCode::OK);
+        let logs = body["data"].as_array().unwrap();
+
+        // Should have both create and update logs
+        let actions: Vec<&str> = logs.iter().map(|l| l["action"].as_str().unwrap()).collect();
+        assert!(actions.contains(&"organization.create"));
+        assert!(actions.contains(&"organization.update"));
+    }
+
+    // ============================================================================
+    // Email Send Log Tests
+    // ============================================================================
+
+    #[tokio::test]
+    async fn test_list_email_log_filters() {
+        use crate::{
+            db::EmailLogRepo,
+            models::{EmailKind, EmailSendStatus, NewEmailLogEntry},
+        };
+
+        let (app, state) = test_app_with_state().await;
+        let org_slug = create_org(&app, "email-log-org").await;
+        let (_, org) = get_json(&app, &format!("/admin/v1/organizations/{}", org_slug)).await;
+        let org_id: uuid::Uuid = org["id"].as_str().unwrap().parse().unwrap();
+
+        let email_log = state.db.as_ref().unwrap().email_log();
+        for (org_id, kind, status) in [
+            (Some(org_id), EmailKind::Invitation, EmailSendStatus::Sent),
+            (Some(org_id), EmailKind::SpendAlert, EmailSendStatus::Failed),
+            (None, EmailKind::DlqFailure, EmailSendStatus::Sent),
+        ] {
+            email_log
+                .create(NewEmailLogEntry {
+                    org_id,
+                    kind,
+                    from_address: "noreply@example.com".to_string(),
+                    recipients: vec!["ops@example.com".to_string()],
+                    subject: "Subject".to_string(),
+                    status,
+                    error: None,
+                })
+                .await
+                .unwrap();
+        }
+
+        let (status, body) = get_json(&app, "/admin/v1/email-log").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"].as_array().unwrap().len(), 3);
+
+        let (status, body) = get_json(
+            &app,
+            &format!("/admin/v1/email-log?org_slug={}&status=failed", org_slug),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let entries = body["data"].as_array().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0]["kind"], "spend_alert");
+        assert_eq!(entries[0]["recipients"], json!(["ops@example.com"]));
+
+        let (status, _) = get_json(&app, "/admin/v1/email-log?org_slug=missing").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    // ============================================================================
+    // Scheduled Report Run Tests
+    // ============================================================================
+
+    #[tokio::test]
+    async fn test_list_report_runs_empty() {
+        let app = test_app().await;
+
+        let (status, body) = get_json(&app, "/admin/v1/report-runs").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"].as_array().unwrap().len(), 0);
+        assert_eq!(body["pagination"]["has_more"], false);
+    }
+
+    #[tokio::test]
+    async fn test_list_report_runs_invalid_direction() {
+        let app = test_app().await;
+
+        let (status, _) = get_json(&app, "/admin/v1/report-runs?direction=sideways").await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_get_report_run_not_found() {
+        let app = test_app().await;
+
+        let (status, _) = get_json(
+            &app,
+            &format!("/admin/v1/report-runs/{}", uuid::Uuid::new_v4()),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    // ============================================================================
+    // Shadow Traffic Result Tests
+    // ============================================================================
+
+    #[cfg(feature = "server")]
+    #[tokio::test]
+    async fn test_shadow_results_empty() {
+        let app = test_app().await;
+
+        let (status, body) = get_json(&app, "/admin/v1/shadow-results?rule=azure").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"].as_array().unwrap().len(), 0);
+        assert_eq!(body["pagination"]["has_more"], false);
+
+        let (status, body) = get_json(&app, "/admin/v1/shadow-results/report").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"].as_array().unwrap().len(), 0);
+
+        let (status, body) = delete_json(&app, "/admin/v1/shadow-results?rule=azure").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["deleted"], 0);
+    }
+
+    #[cfg(feature = "server")]
+    #[tokio::test]
+    async fn test_get_shadow_result_not_found() {
+        let app = test_app().await;
+
+        let (status, _) = get_json(
+            &app,
+            &format!("/admin/v1/shadow-results/{}", uuid::Uuid::new_v4()),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    // ============================================================================
+    // Usage Anomaly Tests
+    // ============================================================================
+
+    #[cfg(feature = "server")]
+    #[tokio::test]
+    async fn test_usage_anomalies_empty() {
+        let app = test_app().await;
+
+        let (status, body) = get_json(
+            &app,
+            "/admin/v1/anomalies?kind=spend_spike&unacknowledged=true",
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"].as_array().unwrap().len(), 0);
+        assert_eq!(body["pagination"]["has_more"], false);
+
+        let (status, _) = get_json(&app, "/admin/v1/anomalies?kind=bogus").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[cfg(feature = "server")]
+    #[tokio::test]
+    async fn test_acknowledge_usage_anomaly_not_found() {
         let app = test_app().await;
 
-        // Invalid direction should return 400
-        let (status, body) = get_json(&app, "/admin/v1/audit-logs?direction=invalid").await;
+        let (status, _) = post_json(
+            &app,
+            &format!("/admin/v1/anomalies/{}/acknowledge", uuid::Uuid::new_v4()),
+            json!({}),
+        )
+        .await;
 
-        assert_eq!(status, StatusCode::BAD_REQUEST);
-        assert!(
-            body["error"]["message"]
-                .as_str()
-                .unwrap()
-                .contains("Invalid direction")
-        );
+        assert_eq!(status, StatusCode::NOT_FOUND);
     }
 
+    // ============================================================================
+    // Vector Store Sync Tests
+    // ============================================================================
+
     #[tokio::test]
-    async fn test_get_audit_log_by_id() {
+    async fn test_list_vector_store_syncs_unknown_store() {
         let app = test_app().await;
 
-        // Create an organization to generate an audit log
-        let (status, _) = post_json(
+        let (status, _) = get_json(
             &app,
-            "/admin/v1/organizations",
-            json!({"slug": "get-audit-org", "name": "Get Audit Org"}),
+            &format!("/admin/v1/vector-stores/{}/syncs", uuid::Uuid::new_v4()),
         )
         .await;
-        assert_eq!(status, StatusCode::CREATED);
 
-        // List audit logs to get an ID
-        let (_, list_body) = get_json(&app, "/admin/v1/audit-logs").await;
-        let logs = list_body["data"].as_array().unwrap();
-        assert!(!logs.is_empty());
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
 
-        let log_id = logs[0]["id"].as_str().unwrap();
+    #[tokio::test]
+    async fn test_list_vector_store_syncs_invalid_direction() {
+        let app = test_app().await;
 
-        // Get the specific audit log by ID
-        let (status, body) = get_json(&app, &format!("/admin/v1/audit-logs/{}", log_id)).await;
+        let (status, _) = get_json(
+            &app,
+            &format!(
+                "/admin/v1/vector-stores/{}/syncs?direction=sideways",
+                uuid::Uuid::new_v4()
+            ),
+        )
+        .await;
 
-        assert_eq!(status, StatusCode::OK);
-        assert_eq!(body["id"], log_id);
-        assert!(body["action"].is_string());
-        assert!(body["resource_type"].is_string());
-        assert!(body["timestamp"].is_string());
+        assert_eq!(status, StatusCode::BAD_REQUEST);
     }
 
     #[tokio::test]
-    async fn test_get_audit_log_not_found() {
+    async fn test_get_vector_store_sync_unknown_store() {
         let app = test_app().await;
 
-        let fake_id = "00000000-0000-0000-0000-000000000000";
-        let (status, body) = get_json(&app, &format!("/admin/v1/audit-logs/{}", fake_id)).await;
+        let (status, _) = get_json(
+            &app,
+            &format!(
+                "/admin/v1/vector-stores/{}/syncs/{}",
+                uuid::Uuid::new_v4(),
+                uuid::Uuid::new_v4()
+            ),
+        )
+        .await;
 
         assert_eq!(status, StatusCode::NOT_FOUND);
-        assert!(
-            body["error"]["message"]
-                .as_str()
-                .unwrap()
-                .contains("not found")
-        );
+    }
+
+    // ============================================================================
+    // Client Certificate Mapping Tests
+    // ============================================================================
+
+    async fn create_service_account(app: &axum::Router, org_slug: &str, slug: &str) {
+        let (status, _) = post_json(
+            app,
+            &format!("/admin/v1/organizations/{}/service-accounts", org_slug),
+            json!({"slug": slug, "name": slug}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
     }
 
     #[tokio::test]
-    async fn test_get_audit_log_invalid_id() {
+    async fn test_client_cert_mapping_lifecycle() {
         let app = test_app().await;
+        let org_slug = create_org(&app, "mtls-org").await;
+        create_service_account(&app, &org_slug, "billing").await;
+        let base = format!(
+            "/admin/v1/organizations/{}/service-accounts/billing/client-certs",
+            org_slug
+        );
 
-        let (status, _) = get_json(&app, "/admin/v1/audit-logs/not-a-uuid").await;
+        let (status, created) = post_json(
+            &app,
+            &base,
+            json!({"match_type": "fingerprint_sha256", "match_value": "AB:CD:EF"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(created["match_value"], "abcdef");
 
-        // Invalid UUID format should return 400
-        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, body) = get_json(&app, &base).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"].as_array().unwrap().len(), 1);
+
+        let id = created["id"].as_str().unwrap();
+        let (status, _) = delete_json(&app, &format!("{}/{}", base, id)).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, body) = get_json(&app, &base).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"].as_array().unwrap().len(), 0);
     }
 
     #[tokio::test]
-    async fn test_list_audit_logs_multiple_actions() {
+    async fn test_client_cert_mapping_duplicate_conflicts() {
         let app = test_app().await;
+        let org_slug = create_org(&app, "mtls-dup-org").await;
+        create_service_account(&app, &org_slug, "first").await;
+        create_service_account(&app, &org_slug, "second").await;
+        let mapping = json!({"match_type": "san_dns", "match_value": "svc.internal"});
 
-        // Create organization
-        let (status, org) = post_json(
+        let (status, _) = post_json(
             &app,
-            "/admin/v1/organizations",
-            json!({"slug": "multi-action-org", "name": "Multi Action Org"}),
+            &format!(
+                "/admin/v1/organizations/{}/service-accounts/first/client-certs",
+                org_slug
+            ),
+            mapping.clone(),
         )
         .await;
         assert_eq!(status, StatusCode::CREATED);
-        let org_slug = org["slug"].as_str().unwrap();
 
-        // Update organization (generates organization.update audit log)
-        let (status, _) = patch_json(
+        // Identifiers are normalized, so case differences still conflict
+        let (status, _) = post_json(
             &app,
-            &format!("/admin/v1/organizations/{}", org_slug),
-            json!({"name": "Updated Multi Action Org"}),
+            &format!(
+                "/admin/v1/organizations/{}/service-accounts/second/client-certs",
+                org_slug
+            ),
+            json!({"match_type": "san_dns", "match_value": "SVC.internal"}),
         )
         .await;
-        assert_eq!(status, StatusCode::OK);
+        assert_eq!(status, StatusCode::CONFLICT);
+    }
 
-        // List all audit logs for this org
-        let org_id = org["id"].as_str().unwrap();
-        let (status, body) =
-            get_json(&app, &format!("/admin/v1/audit-logs?org_id={}", org_id)).await;
+    #[tokio::test]
+    async fn test_client_cert_mapping_delete_wrong_service_account() {
+        let app = test_app().await;
+        let org_slug = create_org(&app, "mtls-scope-org").await;
+        create_service_account(&app, &org_slug, "owner").await;
+        create_service_account(&app, &org_slug, "other").await;
 
-        assert_eq!(status, StatusCode::OK);
-        let logs = body["data"].as_array().unwrap();
+        let (status, created) = post_json(
+            &app,
+            &format!(
+                "/admin/v1/organizations/{}/service-accounts/owner/client-certs",
+                org_slug
+            ),
+            json!({"match_type": "subject_cn", "match_value": "owner"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
 
-        // Should have both create and update logs
-        let actions: Vec<&str> = logs.iter().map(|l| l["action"].as_str().unwrap()).collect();
-        assert!(actions.contains(&"organization.create"));
-        assert!(actions.contains(&"organization.update"));
+        let (status, _) = delete_json(
+            &app,
+            &format!(
+                "/admin/v1/organizations/{}/service-accounts/other/client-certs/{}",
+                org_slug,
+                created["id"].as_str().unwrap()
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
     }
 
     // ============================================================================
@@ -3743,14 +5935,58 @@ api_key = "sk-test-key"
     async fn test_access_inventory_pagination() {
         let app = test_app().await;
 
-        // Get inventory with limit
-        let (status, body) =
-            get_json(&app, "/admin/v1/access-reviews/inventory?limit=5&offset=0").await;
+        for i in 0..3 {
+            create_user_with_id(&app, &format!("page-user-{}", i)).await;
+        }
+
+        let external_ids = |body: &Value| -> Vec<String> {
+            body["users"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|u| u["external_id"].as_str().unwrap().to_string())
+                .collect()
+        };
+
+        // Walk every page in cursor mode
+        let mut seen = Vec::new();
+        let mut pages = Vec::new();
+        let mut uri = "/admin/v1/access-reviews/inventory?limit=2".to_string();
+        loop {
+            let (status, page) = get_json(&app, &uri).await;
+            assert_eq!(status, StatusCode::OK);
+            assert!(page["users"].as_array().unwrap().len() <= 2);
+            seen.extend(external_ids(&page));
+            pages.push(external_ids(&page));
+            match page["pagination"]["next_cursor"].as_str() {
+                Some(cursor) => {
+                    assert_eq!(page["pagination"]["has_more"], true);
+                    uri = format!(
+                        "/admin/v1/access-reviews/inventory?limit=2&cursor={}",
+                        cursor
+                    );
+                }
+                None => break,
+            }
+        }
+        for i in 0..3 {
+            let id = format!("page-user-{}", i);
+            assert_eq!(seen.iter().filter(|s| **s == id).count(), 1);
+        }
 
+        // Legacy offset mode returns the same page as the cursor
+        let (status, by_offset) =
+            get_json(&app, "/admin/v1/access-reviews/inventory?limit=2&offset=2").await;
         assert_eq!(status, StatusCode::OK);
-        assert!(body["users"].is_array());
-        // The limit should be respected (may have fewer if not enough users)
-        assert!(body["users"].as_array().unwrap().len() <= 5);
+        assert_eq!(external_ids(&by_offset), pages[1]);
+
+        // Invalid cursors are rejected
+        let (status, _) = get_json(
+            &app,
+            "/admin/v1/access-reviews/inventory?cursor=not-a-cursor",
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
     }
 
     #[tokio::test]
@@ -4712,6 +6948,157 @@ ttl_secs = 86400
         assert_eq!(status, StatusCode::NOT_FOUND);
     }
 
+    /// Create a project API key to own usage_log DLQ payloads.
+    async fn create_dlq_api_key(app: &axum::Router, slug: &str) -> String {
+        let org_slug = create_org(app, slug).await;
+        let (_, project_body) = post_json(
+            app,
+            &format!("/admin/v1/organizations/{}/projects", org_slug),
+            json!({"slug": slug, "name": "DLQ Project"}),
+        )
+        .await;
+        let (status, api_key_body) = post_json(
+            app,
+            "/admin/v1/api-keys",
+            json!({
+                "name": format!("{slug}-key"),
+                "owner": {"type": "project", "project_id": project_body["id"]}
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        api_key_body["id"].as_str().unwrap().to_string()
+    }
+
+    fn dlq_usage_payload(api_key_id: &str, provider: &str) -> serde_json::Value {
+        json!({
+            "request_id": format!("req-{}", uuid::Uuid::new_v4()),
+            "api_key_id": api_key_id,
+            "model": "gpt-4",
+            "provider": provider,
+            "input_tokens": 100,
+            "output_tokens": 50,
+            "request_at": chrono::Utc::now().to_rfc3339(),
+            "streamed": false,
+            "cached_tokens": 0,
+            "reasoning_tokens": 0,
+            "cancelled": false
+        })
+    }
+
+    #[tokio::test]
+    async fn test_dlq_retry_with_payload_edit() {
+        let (app, dlq) = test_app_with_dlq().await;
+        let api_key_id = create_dlq_api_key(&app, "dlq-edit").await;
+
+        // The payload is missing `model`, so it can't be replayed as is
+        let mut payload = dlq_usage_payload(&api_key_id, "openai");
+        payload.as_object_mut().unwrap().remove("model");
+        let entry = create_test_dlq_entry("usage_log", &payload.to_string(), "missing field");
+        let entry_id = entry.id;
+        dlq.push(entry).await.unwrap();
+
+        let uri = format!("/admin/v1/dlq/{}/retry", entry_id);
+        let (status, body) = post_json(&app, &uri, json!({})).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(
+            body["error"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("Invalid usage_log payload")
+        );
+
+        let (status, _) = post_json(
+            &app,
+            &uri,
+            json!({"payload": payload, "patch": {"model": "gpt-4"}}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, body) = post_json(&app, &uri, json!({"patch": {"model": "gpt-4"}})).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["success"], true);
+        let (status, _) = get_json(&app, &format!("/admin/v1/dlq/{}", entry_id)).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_dlq_redrive_batch() {
+        let (app, dlq) = test_app_with_dlq().await;
+        let api_key_id = create_dlq_api_key(&app, "dlq-redrive").await;
+
+        let openai = create_test_dlq_entry(
+            "usage_log",
+            &dlq_usage_payload(&api_key_id, "openai").to_string(),
+            "error connecting to server: Connection refused",
+        );
+        let openai_id = openai.id;
+        let anthropic = create_test_dlq_entry(
+            "usage_log",
+            &dlq_usage_payload(&api_key_id, "anthropic").to_string(),
+            "error connecting to server: Connection refused",
+        );
+        let constraint = create_test_dlq_entry(
+            "usage_log",
+            &dlq_usage_payload(&api_key_id, "openai").to_string(),
+            "UNIQUE constraint failed: usage_records.request_id",
+        );
+        let webhook = create_test_dlq_entry("webhook", "{}", "Connection refused");
+        for entry in [openai, anthropic, constraint, webhook] {
+            dlq.push(entry).await.unwrap();
+        }
+
+        // A dry run reports the matches without touching them
+        let (status, body) = post_json(
+            &app,
+            "/admin/v1/dlq/redrive",
+            json!({
+                "filter": {"provider": "openai", "error_class": "connection"},
+                "dry_run": true
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["matched"], 1);
+        assert_eq!(body["succeeded"], 0);
+        assert_eq!(body["entry_ids"], json!([openai_id]));
+
+        let (status, _) = post_json(
+            &app,
+            "/admin/v1/dlq/redrive",
+            json!({"patch": ["not", "an", "object"]}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, body) = post_json(
+            &app,
+            "/admin/v1/dlq/redrive",
+            json!({
+                "filter": {"error_class": "connection"},
+                "patch": {"model": "gpt-4o"}
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["matched"], 3);
+        assert_eq!(body["succeeded"], 2);
+        assert_eq!(body["skipped"], 1);
+        assert_eq!(body["failed"], 0);
+
+        // The constraint failure and the unsupported webhook remain
+        let (_, body) = get_json(&app, "/admin/v1/dlq").await;
+        let remaining: Vec<_> = body["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| e["entry_type"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(remaining.len(), 2);
+        assert!(remaining.contains(&"webhook".to_string()));
+    }
+
     // ============================================================================
     // Template Tests
     // ============================================================================
@@ -5341,6 +7728,12 @@ ttl_secs = 86400
 
     /// Create a test application with a custom config string
     async fn test_app_with_config(config_str: &str) -> axum::Router {
+        test_app_with_config_and_state(config_str).await.0
+    }
+
+    /// Like [`test_app_with_config`], also returning the state behind the
+    /// router.
+    async fn test_app_with_config_and_state(config_str: &str) -> (axum::Router, crate::AppState) {
         #[cfg_attr(not(feature = "sso"), allow(unused_mut))]
         let mut config =
             crate::config::GatewayConfig::parse(config_str).expect("Failed to parse test config");
@@ -5364,7 +7757,7 @@ ttl_secs = 86400
         let state = crate::AppState::new(config.clone())
             .await
             .expect("Failed to create AppState");
-        crate::build_app(&config, state)
+        (crate::build_app(&config, state.clone()), state)
     }
 
     /// Generate a unique in-memory database path for tests
@@ -5496,177 +7889,631 @@ border = "#e2e8f0"
             r##"
 {}
 
-[ui.branding.colors_dark]
-primary = "#60a5fa"
-background = "#0f172a"
-foreground = "#f8fafc"
-"##,
-            unique_db_config()
-        );
+[ui.branding.colors_dark]
+primary = "#60a5fa"
+background = "#0f172a"
+foreground = "#f8fafc"
+"##,
+            unique_db_config()
+        );
+
+        let app = test_app_with_config(&config_str).await;
+        let (status, body) = get_json(&app, "/admin/v1/ui/config").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert!(body["branding"]["colors_dark"].is_object());
+        assert_eq!(body["branding"]["colors_dark"]["primary"], "#60a5fa");
+        assert_eq!(body["branding"]["colors_dark"]["background"], "#0f172a");
+        assert_eq!(body["branding"]["colors_dark"]["foreground"], "#f8fafc");
+    }
+
+    #[tokio::test]
+    async fn test_get_ui_config_fonts() {
+        let config_str = format!(
+            r#"
+{}
+
+[ui.branding.fonts]
+heading = "Inter"
+body = "Roboto"
+mono = "JetBrains Mono"
+
+[[ui.branding.fonts.custom]]
+name = "CustomFont"
+url = "https://example.com/fonts/custom.woff2"
+weight = "400 700"
+style = "normal"
+"#,
+            unique_db_config()
+        );
+
+        let app = test_app_with_config(&config_str).await;
+        let (status, body) = get_json(&app, "/admin/v1/ui/config").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["branding"]["fonts"]["heading"], "Inter");
+        assert_eq!(body["branding"]["fonts"]["body"], "Roboto");
+        assert_eq!(body["branding"]["fonts"]["mono"], "JetBrains Mono");
+
+        let custom = &body["branding"]["fonts"]["custom"];
+        assert!(custom.is_array());
+        assert_eq!(custom[0]["name"], "CustomFont");
+        assert_eq!(custom[0]["url"], "https://example.com/fonts/custom.woff2");
+        assert_eq!(custom[0]["weight"], "400 700");
+        assert_eq!(custom[0]["style"], "normal");
+    }
+
+    #[tokio::test]
+    async fn test_get_ui_config_footer() {
+        let config_str = format!(
+            r#"
+{}
+
+[ui.branding]
+footer_text = "© 2024 Acme Corp. All rights reserved."
+
+[[ui.branding.footer_links]]
+label = "Privacy Policy"
+url = "https://example.com/privacy"
+
+[[ui.branding.footer_links]]
+label = "Terms of Service"
+url = "https://example.com/terms"
+"#,
+            unique_db_config()
+        );
+
+        let app = test_app_with_config(&config_str).await;
+        let (status, body) = get_json(&app, "/admin/v1/ui/config").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body["branding"]["footer_text"],
+            "© 2024 Acme Corp. All rights reserved."
+        );
+
+        let links = body["branding"]["footer_links"].as_array().unwrap();
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0]["label"], "Privacy Policy");
+        assert_eq!(links[0]["url"], "https://example.com/privacy");
+        assert_eq!(links[1]["label"], "Terms of Service");
+        assert_eq!(links[1]["url"], "https://example.com/terms");
+    }
+
+    #[tokio::test]
+    async fn test_get_ui_config_version_shown() {
+        let config_str = format!(
+            r#"
+{}
+
+[ui.branding]
+show_version = true
+"#,
+            unique_db_config()
+        );
+
+        let app = test_app_with_config(&config_str).await;
+        let (status, body) = get_json(&app, "/admin/v1/ui/config").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["branding"]["show_version"], true);
+        assert!(body["branding"]["version"].is_string());
+        // Version should be the cargo package version
+        let version = body["branding"]["version"].as_str().unwrap();
+        assert!(!version.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_ui_config_version_hidden() {
+        let config_str = format!(
+            r#"
+{}
+
+[ui.branding]
+show_version = false
+"#,
+            unique_db_config()
+        );
+
+        let app = test_app_with_config(&config_str).await;
+        let (status, body) = get_json(&app, "/admin/v1/ui/config").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["branding"]["show_version"], false);
+        // Version should not be included when show_version is false
+        assert_eq!(body["branding"]["version"], Value::Null);
+    }
+
+    #[tokio::test]
+    async fn test_get_ui_config_chat_settings() {
+        let config_str = format!(
+            r#"
+{}
+
+[ui.chat]
+enabled = true
+default_model = "gpt-4o"
+available_models = ["gpt-4o", "gpt-4o-mini", "claude-3-opus"]
+
+[ui.chat.file_uploads]
+enabled = true
+max_size_bytes = 52428800
+allowed_types = ["image/png", "image/jpeg", "application/pdf"]
+"#,
+            unique_db_config()
+        );
+
+        let app = test_app_with_config(&config_str).await;
+        let (status, body) = get_json(&app, "/admin/v1/ui/config").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["chat"]["enabled"], true);
+        assert_eq!(body["chat"]["default_model"], "gpt-4o");
+
+        let models = body["chat"]["available_models"].as_array().unwrap();
+        assert_eq!(models.len(), 3);
+        assert!(models.contains(&json!("gpt-4o")));
+        assert!(models.contains(&json!("claude-3-opus")));
+
+        assert_eq!(body["chat"]["file_uploads_enabled"], true);
+        assert_eq!(body["chat"]["max_file_size_bytes"], 52428800);
+
+        let allowed_types = body["chat"]["allowed_file_types"].as_array().unwrap();
+        assert_eq!(allowed_types.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_get_ui_config_chat_presets() {
+        let app = test_app().await;
+
+        let (status, _) = post_json(
+            &app,
+            "/admin/v1/organizations",
+            json!({"slug": "preset-org", "name": "Preset Org"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let (status, _) = patch_json(
+            &app,
+            "/admin/v1/organizations/preset-org",
+            json!({"request_defaults": {
+                "model": "openai/gpt-4o-mini",
+                "temperature": 0.4,
+                "system_prompt": "Internal instructions"
+            }}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        // Without an org there are no presets
+        let (_, body) = get_json(&app, "/admin/v1/ui/config").await;
+        assert_eq!(body["chat"]["presets"], Value::Null);
+
+        let (status, body) = get_json(&app, "/admin/v1/ui/config?org=preset-org").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body["chat"]["presets"]["default_model"],
+            "openai/gpt-4o-mini"
+        );
+        assert_eq!(body["chat"]["presets"]["temperature"], 0.4);
+        assert_eq!(body["chat"]["presets"]["has_system_prompt"], true);
+        // The prompt text itself is never exposed
+        assert!(!body.to_string().contains("Internal instructions"));
+    }
+
+    #[tokio::test]
+    async fn test_update_organization_rejects_invalid_request_defaults() {
+        let app = test_app().await;
+
+        let (status, _) = post_json(
+            &app,
+            "/admin/v1/organizations",
+            json!({"slug": "bad-defaults", "name": "Bad Defaults"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let (status, _) = patch_json(
+            &app,
+            "/admin/v1/organizations/bad-defaults",
+            json!({"request_defaults": {"temperature": 5.0}}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_org_network_policy_crud() {
+        let app = test_app().await;
+
+        let (status, _) = post_json(
+            &app,
+            "/admin/v1/organizations",
+            json!({"slug": "netpol", "name": "Network Policy Org"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let uri = "/admin/v1/organizations/netpol/network-policy";
+        let (status, _) = get_json(&app, uri).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, _) =
+            put_json(&app, uri, json!({"ip_allowlist": ["10.0.0.0/8", "nope"]})).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = put_json(&app, uri, json!({"ip_allowlist": []})).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, body) = put_json(
+            &app,
+            uri,
+            json!({"ip_allowlist": ["10.0.0.0/8", "203.0.113.7"]}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["ip_allowlist"][1], "203.0.113.7");
+
+        let (status, body) = get_json(&app, uri).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["ip_allowlist"][0], "10.0.0.0/8");
+
+        let (_, org) = get_json(&app, "/admin/v1/organizations/netpol").await;
+        assert_eq!(org["network_policy"]["ip_allowlist"][0], "10.0.0.0/8");
+
+        let (status, _) = delete_json(&app, uri).await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = get_json(&app, uri).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, _) = delete_json(&app, uri).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_labels_and_label_selectors() {
+        let app = test_app().await;
+
+        for slug in ["labels-a", "labels-b"] {
+            let (status, _) = post_json(
+                &app,
+                "/admin/v1/organizations",
+                json!({"slug": slug, "name": slug}),
+            )
+            .await;
+            assert_eq!(status, StatusCode::CREATED);
+        }
+        let (status, _) = post_json(
+            &app,
+            "/admin/v1/organizations/labels-a/projects",
+            json!({"slug": "web", "name": "Web"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let uri = "/admin/v1/organizations/labels-a/labels";
+        let (status, _) = put_json(&app, uri, json!({"labels": {"Env": "prod"}})).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = put_json(&app, uri, json!({"labels": {"env": "a,b"}})).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
 
-        let app = test_app_with_config(&config_str).await;
-        let (status, body) = get_json(&app, "/admin/v1/ui/config").await;
+        let (status, body) =
+            put_json(&app, uri, json!({"labels": {"env": "prod", "tier": "web"}})).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["labels"]["env"], "prod");
 
+        let (status, body) = put_json(
+            &app,
+            "/admin/v1/organizations/labels-a/projects/web/labels",
+            json!({"labels": {"env": "prod"}}),
+        )
+        .await;
         assert_eq!(status, StatusCode::OK);
-        assert!(body["branding"]["colors_dark"].is_object());
-        assert_eq!(body["branding"]["colors_dark"]["primary"], "#60a5fa");
-        assert_eq!(body["branding"]["colors_dark"]["background"], "#0f172a");
-        assert_eq!(body["branding"]["colors_dark"]["foreground"], "#f8fafc");
-    }
+        assert_eq!(body["labels"]["env"], "prod");
 
-    #[tokio::test]
-    async fn test_get_ui_config_fonts() {
-        let config_str = format!(
-            r#"
-{}
+        let (status, body) = get_json(&app, "/admin/v1/organizations?label=env=prod").await;
+        assert_eq!(status, StatusCode::OK);
+        let data = body["data"].as_array().unwrap();
+        assert_eq!(data.len(), 1);
+        assert_eq!(data[0]["slug"], "labels-a");
 
-[ui.branding.fonts]
-heading = "Inter"
-body = "Roboto"
-mono = "JetBrains Mono"
+        let (_, body) = get_json(&app, "/admin/v1/organizations?label=env=prod,tier=db").await;
+        assert!(body["data"].as_array().unwrap().is_empty());
 
-[[ui.branding.fonts.custom]]
-name = "CustomFont"
-url = "https://example.com/fonts/custom.woff2"
-weight = "400 700"
-style = "normal"
-"#,
-            unique_db_config()
-        );
+        let (_, body) = get_json(
+            &app,
+            "/admin/v1/organizations/labels-a/projects?label=env=prod",
+        )
+        .await;
+        assert_eq!(body["data"].as_array().unwrap().len(), 1);
 
-        let app = test_app_with_config(&config_str).await;
-        let (status, body) = get_json(&app, "/admin/v1/ui/config").await;
+        let (status, _) = get_json(&app, "/admin/v1/organizations?label=env").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
 
+        // An empty object removes all labels
+        let (status, body) = put_json(&app, uri, json!({"labels": {}})).await;
         assert_eq!(status, StatusCode::OK);
-        assert_eq!(body["branding"]["fonts"]["heading"], "Inter");
-        assert_eq!(body["branding"]["fonts"]["body"], "Roboto");
-        assert_eq!(body["branding"]["fonts"]["mono"], "JetBrains Mono");
+        assert!(body["labels"].as_object().unwrap().is_empty());
+        let (_, body) = get_json(&app, "/admin/v1/organizations?label=env=prod").await;
+        assert!(body["data"].as_array().unwrap().is_empty());
 
-        let custom = &body["branding"]["fonts"]["custom"];
-        assert!(custom.is_array());
-        assert_eq!(custom[0]["name"], "CustomFont");
-        assert_eq!(custom[0]["url"], "https://example.com/fonts/custom.woff2");
-        assert_eq!(custom[0]["weight"], "400 700");
-        assert_eq!(custom[0]["style"], "normal");
+        let (status, _) = put_json(
+            &app,
+            "/admin/v1/organizations/missing/labels",
+            json!({"labels": {}}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
     }
 
     #[tokio::test]
-    async fn test_get_ui_config_footer() {
-        let config_str = format!(
-            r#"
-{}
-
-[ui.branding]
-footer_text = "© 2024 Acme Corp. All rights reserved."
+    async fn test_parameter_governance_crud() {
+        let app = test_app().await;
 
-[[ui.branding.footer_links]]
-label = "Privacy Policy"
-url = "https://example.com/privacy"
+        let (status, _) = post_json(
+            &app,
+            "/admin/v1/organizations",
+            json!({"slug": "governed", "name": "Governed Org"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let (status, _) = post_json(
+            &app,
+            "/admin/v1/organizations/governed/projects",
+            json!({"slug": "app", "name": "App"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
 
-[[ui.branding.footer_links]]
-label = "Terms of Service"
-url = "https://example.com/terms"
-"#,
-            unique_db_config()
-        );
+        let rules = json!({"rules": [{
+            "models": ["gpt-4*"],
+            "max_tokens": 2048,
+            "max_temperature": 1.0,
+            "forbidden_params": ["logit_bias"],
+            "on_violation": "reject",
+        }]});
+
+        for uri in [
+            "/admin/v1/organizations/governed/parameter-governance",
+            "/admin/v1/organizations/governed/projects/app/parameter-governance",
+        ] {
+            let (status, _) = get_json(&app, uri).await;
+            assert_eq!(status, StatusCode::NOT_FOUND);
+
+            let (status, _) = put_json(&app, uri, json!({"rules": []})).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            let (status, _) = put_json(
+                &app,
+                uri,
+                json!({"rules": [{"min_temperature": 1.5, "max_temperature": 0.5}]}),
+            )
+            .await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
 
-        let app = test_app_with_config(&config_str).await;
-        let (status, body) = get_json(&app, "/admin/v1/ui/config").await;
+            let (status, body) = put_json(&app, uri, rules.clone()).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(body["rules"][0]["on_violation"], "reject");
 
-        assert_eq!(status, StatusCode::OK);
-        assert_eq!(
-            body["branding"]["footer_text"],
-            "© 2024 Acme Corp. All rights reserved."
-        );
+            let (status, body) = get_json(&app, uri).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(body, rules);
 
-        let links = body["branding"]["footer_links"].as_array().unwrap();
-        assert_eq!(links.len(), 2);
-        assert_eq!(links[0]["label"], "Privacy Policy");
-        assert_eq!(links[0]["url"], "https://example.com/privacy");
-        assert_eq!(links[1]["label"], "Terms of Service");
-        assert_eq!(links[1]["url"], "https://example.com/terms");
+            let (status, _) = delete_json(&app, uri).await;
+            assert_eq!(status, StatusCode::OK);
+            let (status, _) = get_json(&app, uri).await;
+            assert_eq!(status, StatusCode::NOT_FOUND);
+        }
     }
 
     #[tokio::test]
-    async fn test_get_ui_config_version_shown() {
-        let config_str = format!(
-            r#"
-{}
+    async fn test_model_access_crud() {
+        let app = test_app().await;
 
-[ui.branding]
-show_version = true
-"#,
-            unique_db_config()
-        );
+        let (status, _) = post_json(
+            &app,
+            "/admin/v1/organizations",
+            json!({"slug": "model-access", "name": "Model Access Org"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let (status, _) = post_json(
+            &app,
+            "/admin/v1/organizations/model-access/projects",
+            json!({"slug": "app", "name": "App"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
 
-        let app = test_app_with_config(&config_str).await;
-        let (status, body) = get_json(&app, "/admin/v1/ui/config").await;
+        let policy = json!({
+            "allowed_models": ["gpt-4*"],
+            "denied_providers": ["bedrock"],
+        });
 
-        assert_eq!(status, StatusCode::OK);
-        assert_eq!(body["branding"]["show_version"], true);
-        assert!(body["branding"]["version"].is_string());
-        // Version should be the cargo package version
-        let version = body["branding"]["version"].as_str().unwrap();
-        assert!(!version.is_empty());
+        for uri in [
+            "/admin/v1/organizations/model-access/model-access",
+            "/admin/v1/organizations/model-access/projects/app/model-access",
+        ] {
+            let (status, _) = get_json(&app, uri).await;
+            assert_eq!(status, StatusCode::NOT_FOUND);
+
+            let (status, _) = put_json(&app, uri, json!({})).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            let (status, _) = put_json(&app, uri, json!({"denied_models": ["*"]})).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+
+            let (status, body) = put_json(&app, uri, policy.clone()).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(body, policy);
+
+            let (status, body) = get_json(&app, uri).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(body, policy);
+
+            let (status, _) = delete_json(&app, uri).await;
+            assert_eq!(status, StatusCode::OK);
+            let (status, _) = delete_json(&app, uri).await;
+            assert_eq!(status, StatusCode::NOT_FOUND);
+        }
+
+        let (status, _) = get_json(
+            &app,
+            "/admin/v1/organizations/model-access/allowed-models?project=missing",
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
     }
 
     #[tokio::test]
-    async fn test_get_ui_config_version_hidden() {
-        let config_str = format!(
-            r#"
-{}
+    async fn test_model_degradation_crud() {
+        let app = test_app().await;
 
-[ui.branding]
-show_version = false
-"#,
-            unique_db_config()
-        );
+        let (status, _) = post_json(
+            &app,
+            "/admin/v1/organizations",
+            json!({"slug": "degrade", "name": "Degrade Org"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
 
-        let app = test_app_with_config(&config_str).await;
-        let (status, body) = get_json(&app, "/admin/v1/ui/config").await;
+        let uri = "/admin/v1/organizations/degrade/model-degradation";
+        let (status, _) = get_json(&app, uri).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, _) = put_json(&app, uri, json!({"ladders": []})).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = put_json(
+            &app,
+            uri,
+            json!({"ladders": [{"model": "gpt-4o", "downgrades": ["openai/gpt-4o"]}]}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
 
+        let policy = json!({
+            "ladders": [{"model": "openai/gpt-4o", "downgrades": ["openai/gpt-4o-mini"]}],
+            "triggers": ["rate_limited", "circuit_open"],
+        });
+        let (status, body) = put_json(&app, uri, policy.clone()).await;
         assert_eq!(status, StatusCode::OK);
-        assert_eq!(body["branding"]["show_version"], false);
-        // Version should not be included when show_version is false
-        assert_eq!(body["branding"]["version"], Value::Null);
+        assert_eq!(body, policy);
+
+        let (status, body) = get_json(&app, uri).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, policy);
+
+        let (status, _) = delete_json(&app, uri).await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = delete_json(&app, uri).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
     }
 
     #[tokio::test]
-    async fn test_get_ui_config_chat_settings() {
+    async fn test_entitlements_crud_and_limits() {
         let config_str = format!(
             r#"
 {}
 
-[ui.chat]
+[features.entitlements]
 enabled = true
-default_model = "gpt-4o"
-available_models = ["gpt-4o", "gpt-4o-mini", "claude-3-opus"]
+default_plan = "starter"
 
-[ui.chat.file_uploads]
-enabled = true
-max_size_bytes = 52428800
-allowed_types = ["image/png", "image/jpeg", "application/pdf"]
+[features.entitlements.plans.starter]
+max_projects = 1
+max_api_keys = 1
+
+[features.entitlements.plans.growth]
+file_search = true
+max_projects = 2
 "#,
             unique_db_config()
         );
-
         let app = test_app_with_config(&config_str).await;
-        let (status, body) = get_json(&app, "/admin/v1/ui/config").await;
 
+        let (status, org) = post_json(
+            &app,
+            "/admin/v1/organizations",
+            json!({"slug": "planned", "name": "Planned Org"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let org_id = org["id"].as_str().unwrap();
+
+        let uri = "/admin/v1/organizations/planned/entitlements";
+        let (status, body) = get_json(&app, uri).await;
         assert_eq!(status, StatusCode::OK);
-        assert_eq!(body["chat"]["enabled"], true);
-        assert_eq!(body["chat"]["default_model"], "gpt-4o");
+        assert!(body.get("assigned").is_none());
+        assert_eq!(body["effective"]["plan"], "starter");
+        assert_eq!(body["effective"]["file_search"], false);
+        assert_eq!(body["usage"]["projects"], 0);
 
-        let models = body["chat"]["available_models"].as_array().unwrap();
-        assert_eq!(models.len(), 3);
-        assert!(models.contains(&json!("gpt-4o")));
-        assert!(models.contains(&json!("claude-3-opus")));
+        // The starter plan allows one project and one API key
+        let projects_uri = "/admin/v1/organizations/planned/projects";
+        let (status, _) = post_json(
+            &app,
+            projects_uri,
+            json!({"slug": "first", "name": "First"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let (status, body) = post_json(
+            &app,
+            projects_uri,
+            json!({"slug": "second", "name": "Second"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert!(
+            body["error"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("'starter' plan")
+        );
 
-        assert_eq!(body["chat"]["file_uploads_enabled"], true);
-        assert_eq!(body["chat"]["max_file_size_bytes"], 52428800);
+        let key =
+            |name: &str| json!({"name": name, "owner": {"type": "organization", "org_id": org_id}});
+        let (status, _) = post_json(&app, "/admin/v1/api-keys", key("one")).await;
+        assert_eq!(status, StatusCode::CREATED);
+        let (status, _) = post_json(&app, "/admin/v1/api-keys", key("two")).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
 
-        let allowed_types = body["chat"]["allowed_file_types"].as_array().unwrap();
-        assert_eq!(allowed_types.len(), 3);
+        let (status, _) = put_json(&app, uri, json!({})).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = put_json(&app, uri, json!({"plan": "enterprise"})).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, body) =
+            put_json(&app, uri, json!({"plan": "growth", "max_api_keys": 3})).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["assigned"]["plan"], "growth");
+        assert_eq!(body["effective"]["file_search"], true);
+        assert_eq!(body["effective"]["max_projects"], 2);
+        assert_eq!(body["effective"]["max_api_keys"], 3);
+        assert_eq!(body["usage"]["projects"], 1);
+        assert_eq!(body["usage"]["api_keys"], 1);
+
+        let (status, _) = post_json(
+            &app,
+            projects_uri,
+            json!({"slug": "second", "name": "Second"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let (status, _) = post_json(&app, "/admin/v1/api-keys", key("two")).await;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let (status, _) = delete_json(&app, uri).await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, body) = get_json(&app, uri).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["effective"]["plan"], "starter");
+        let (status, _) = delete_json(&app, uri).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_entitlements_require_feature() {
+        let app = test_app().await;
+        let (status, _) = get_json(&app, "/admin/v1/organizations/local/entitlements").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
     }
 
     #[tokio::test]
@@ -6161,4 +9008,52 @@ url = "https://mcp.internal.example.com/mcp"
             );
         }
     }
+
+    #[tokio::test]
+    async fn test_step_up_lockout_without_cache() {
+        use crate::middleware::util::step_up::{TotpAttempt, attempt_totp};
+
+        let config = format!(
+            "{}\n[auth.step_up]\nmax_failed_attempts = 2\n",
+            unique_db_config()
+        );
+        let (app, state) = test_app_with_config_and_state(&config).await;
+        assert!(state.cache.is_none());
+
+        let user_id: uuid::Uuid = create_user_with_id(&app, "step-up-no-cache")
+            .await
+            .parse()
+            .unwrap();
+        let totp = state.db.as_ref().unwrap().admin_totp();
+        totp.upsert_pending(user_id, &crate::auth::totp::generate_secret())
+            .await
+            .unwrap();
+        assert!(totp.mark_verified(user_id, 0).await.unwrap());
+
+        let services = state.services.as_ref().unwrap();
+        let identity = crate::auth::Identity {
+            external_id: "step-up-no-cache".to_string(),
+            email: None,
+            name: None,
+            user_id: Some(user_id),
+            roles: vec![],
+            idp_groups: vec![],
+            org_ids: vec![],
+            team_ids: vec![],
+            project_ids: vec![],
+        };
+        let client_info = crate::middleware::ClientInfo::default();
+
+        // Malformed codes never verify, so each one counts as a failure
+        for expected in [
+            TotpAttempt::Invalid,
+            TotpAttempt::Invalid,
+            TotpAttempt::LockedOut,
+        ] {
+            let attempt = attempt_totp(&state, services, &identity, user_id, "bad", &client_info)
+                .await
+                .unwrap();
+            assert_eq!(attempt, expected);
+        }
+    }
 }
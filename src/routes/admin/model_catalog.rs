@@ -0,0 +1,241 @@
+//! Admin API endpoints for custom model catalog entries.
+//!
+//! Entries describe models the models.dev catalog doesn't know, such as
+//! self-hosted or fine-tuned models, or override its data for a model. They
+//! are applied on this node as soon as they change and on other nodes
+//! within 30 seconds.
+
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use axum_valid::Valid;
+use serde_json::json;
+use uuid::Uuid;
+
+use super::{AdminError, AuditActor};
+use crate::{
+    AppState,
+    db::DbPool,
+    middleware::{AdminAuth, AuthzContext, ClientInfo},
+    models::{CreateAuditLog, CreateModelCatalogEntry, ModelCatalogEntry, UpdateModelCatalogEntry},
+};
+
+fn get_db(state: &AppState) -> Result<&DbPool, AdminError> {
+    state.db.as_deref().ok_or(AdminError::DatabaseRequired)
+}
+
+/// Reload this node's custom entries after a change.
+async fn reload(state: &AppState, db: &DbPool) -> Result<(), AdminError> {
+    state.model_catalog.load_custom_entries(db).await?;
+    Ok(())
+}
+
+async fn audit(
+    state: &AppState,
+    admin_auth: &AdminAuth,
+    client_info: ClientInfo,
+    action: &str,
+    entry: &ModelCatalogEntry,
+) {
+    let Some(services) = &state.services else {
+        return;
+    };
+    let actor = AuditActor::from(admin_auth);
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: format!("model_catalog.{action}"),
+            resource_type: "model_catalog_entry".to_string(),
+            resource_id: entry.id,
+            org_id: None,
+            project_id: None,
+            details: json!({ "entry": entry }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+}
+
+/// List custom model catalog entries
+///
+/// Returns entries ordered by provider and model.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/model-catalog",
+    tag = "model-catalog",
+    operation_id = "model_catalog_list",
+    responses(
+        (status = 200, description = "Custom catalog entries", body = Vec<ModelCatalogEntry>),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn list(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+) -> Result<Json<Vec<ModelCatalogEntry>>, AdminError> {
+    authz.require("model_catalog", "list", None, None, None, None)?;
+    let db = get_db(&state)?;
+    Ok(Json(db.model_catalog_entries().list().await?))
+}
+
+/// Add a custom model catalog entry
+///
+/// Adds catalog metadata for a model under a catalog provider ID. Providers
+/// use the entry when their catalog provider (`catalog_provider`, or the one
+/// detected from their type and base URL) matches. The entry takes
+/// precedence over the models.dev entry for the same model.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/model-catalog",
+    tag = "model-catalog",
+    operation_id = "model_catalog_create",
+    request_body = CreateModelCatalogEntry,
+    responses(
+        (status = 201, description = "Entry created", body = ModelCatalogEntry),
+        (status = 400, description = "Invalid entry", body = crate::openapi::ErrorResponse),
+        (status = 409, description = "The model already has an entry", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn create(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Valid(Json(input)): Valid<Json<CreateModelCatalogEntry>>,
+) -> Result<(StatusCode, Json<ModelCatalogEntry>), AdminError> {
+    authz.require("model_catalog", "create", None, None, None, None)?;
+    let db = get_db(&state)?;
+
+    let entry = db.model_catalog_entries().create(input).await?;
+    reload(&state, db).await?;
+
+    audit(&state, &admin_auth, client_info, "create", &entry).await;
+    Ok((StatusCode::CREATED, Json(entry)))
+}
+
+/// Get a custom model catalog entry
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/model-catalog/{id}",
+    tag = "model-catalog",
+    operation_id = "model_catalog_get",
+    params(("id" = Uuid, Path, description = "Entry ID")),
+    responses(
+        (status = 200, description = "Custom catalog entry", body = ModelCatalogEntry),
+        (status = 404, description = "Entry not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn get(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ModelCatalogEntry>, AdminError> {
+    authz.require(
+        "model_catalog",
+        "read",
+        Some(&id.to_string()),
+        None,
+        None,
+        None,
+    )?;
+    let db = get_db(&state)?;
+
+    let entry = db
+        .model_catalog_entries()
+        .get_by_id(id)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("Catalog entry '{id}' not found")))?;
+    Ok(Json(entry))
+}
+
+/// Replace a custom model catalog entry
+///
+/// Replaces the entry's metadata; omitted fields are cleared. The provider
+/// and model can't be changed.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    put,
+    path = "/admin/v1/model-catalog/{id}",
+    tag = "model-catalog",
+    operation_id = "model_catalog_update",
+    params(("id" = Uuid, Path, description = "Entry ID")),
+    request_body = UpdateModelCatalogEntry,
+    responses(
+        (status = 200, description = "Entry updated", body = ModelCatalogEntry),
+        (status = 400, description = "Invalid entry", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Entry not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn update(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path(id): Path<Uuid>,
+    Valid(Json(input)): Valid<Json<UpdateModelCatalogEntry>>,
+) -> Result<Json<ModelCatalogEntry>, AdminError> {
+    authz.require(
+        "model_catalog",
+        "update",
+        Some(&id.to_string()),
+        None,
+        None,
+        None,
+    )?;
+    let db = get_db(&state)?;
+
+    let entry = db
+        .model_catalog_entries()
+        .update(id, input.metadata)
+        .await?;
+    reload(&state, db).await?;
+
+    audit(&state, &admin_auth, client_info, "update", &entry).await;
+    Ok(Json(entry))
+}
+
+/// Delete a custom model catalog entry
+///
+/// The model falls back to its models.dev entry, if any.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    delete,
+    path = "/admin/v1/model-catalog/{id}",
+    tag = "model-catalog",
+    operation_id = "model_catalog_delete",
+    params(("id" = Uuid, Path, description = "Entry ID")),
+    responses(
+        (status = 204, description = "Entry deleted"),
+        (status = 404, description = "Entry not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn delete(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AdminError> {
+    authz.require(
+        "model_catalog",
+        "delete",
+        Some(&id.to_string()),
+        None,
+        None,
+        None,
+    )?;
+    let db = get_db(&state)?;
+
+    let entry = db
+        .model_catalog_entries()
+        .get_by_id(id)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("Catalog entry '{id}' not found")))?;
+    db.model_catalog_entries().delete(id).await?;
+    reload(&state, db).await?;
+
+    audit(&state, &admin_auth, client_info, "delete", &entry).await;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -230,7 +230,13 @@ pub async fn authorize(
             check_owner_create_authz(services, &authz, &owner).await?;
         }
     }
-    check_owner_create_limits(services, &owner, &state.config.limits.resource_limits).await?;
+    check_owner_create_limits(
+        services,
+        &owner,
+        &state.config.limits.resource_limits,
+        &state.config.features.entitlements,
+    )
+    .await?;
 
     if !pkce.allow_plain_method
         && matches!(input.code_challenge_method, PkceCodeChallengeMethod::Plain)
@@ -0,0 +1,95 @@
+//! Observability endpoints.
+//!
+//! Serves Grafana dashboard and Prometheus alert rule definitions generated
+//! from this build's metric names and the configured providers and SLOs, and
+//! the current status of those SLOs.
+
+use axum::{Extension, Json, extract::State};
+use serde::Serialize;
+
+use super::AdminError;
+use crate::{
+    AppState,
+    middleware::AuthzContext,
+    observability::{
+        grafana,
+        slo::{self, SloStatus},
+    },
+};
+
+/// Generated observability assets.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct GrafanaProvisioningResponse {
+    /// Grafana dashboard JSON model, ready to import or drop into a
+    /// file-based dashboard provider.
+    #[cfg_attr(feature = "utoipa", schema(value_type = Object))]
+    pub dashboard: serde_json::Value,
+    /// Prometheus alerting rule groups (`groups: [...]`). JSON is valid YAML,
+    /// so this can be saved directly as a rule file.
+    #[cfg_attr(feature = "utoipa", schema(value_type = Object))]
+    pub alert_rules: serde_json::Value,
+}
+
+/// Get Grafana dashboard and Prometheus alert rules
+///
+/// Both are generated from the metric names recorded by this gateway build
+/// and the providers in the static configuration, so re-fetching after an
+/// upgrade or config change keeps dashboards and alerts in sync.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/observability/grafana",
+    tag = "observability",
+    responses(
+        (status = 200, description = "Dashboard and alert rules", body = GrafanaProvisioningResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn grafana(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+) -> Result<Json<GrafanaProvisioningResponse>, AdminError> {
+    // The assets embed configured provider names
+    authz.require("provider", "read", None, None, None, None)?;
+
+    let providers = &state.config.providers;
+    Ok(Json(GrafanaProvisioningResponse {
+        dashboard: grafana::dashboard(providers),
+        alert_rules: grafana::alert_rules(providers, &state.config.observability.slos),
+    }))
+}
+
+/// Status of the configured SLOs.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct SlosResponse {
+    /// One entry per `[[observability.slos]]`, in config order.
+    pub slos: Vec<SloStatus>,
+}
+
+/// Get SLO compliance and burn rates
+///
+/// Compliance is the fraction of good requests over each SLO's window. Burn
+/// rates compare the bad fraction over 5m, 30m, 1h and 6h to the error budget;
+/// `fast_burn` and `slow_burn` flag the multiwindow alert conditions. Counts
+/// are kept per gateway instance.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/slos",
+    tag = "observability",
+    responses(
+        (status = 200, description = "SLO status", body = SlosResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn slos(
+    Extension(authz): Extension<AuthzContext>,
+) -> Result<Json<SlosResponse>, AdminError> {
+    // SLOs are defined over provider traffic
+    authz.require("provider", "list", None, None, None, None)?;
+
+    let slos = slo::tracker()
+        .map(|t| t.current_status())
+        .unwrap_or_default();
+    Ok(Json(SlosResponse { slos }))
+}
@@ -0,0 +1,534 @@
+//! Admin API endpoints for per-organization request policies.
+//!
+//! Request policies are CEL conditions evaluated against every data-plane
+//! request from an organization's callers. A matching policy allows, denies
+//! or modifies the request before it reaches a provider.
+
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use axum_valid::Valid;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+use validator::Validate;
+
+use super::{AuditActor, error::AdminError};
+use crate::{
+    AppState,
+    authz::{
+        RequestPolicyEvaluation, RequestPolicySubject, TimeContext, compile_request_policies,
+        evaluate_request_policies,
+    },
+    config::TransformEndpoint,
+    middleware::{AdminAuth, AuthzContext, ClientInfo},
+    models::{CreateAuditLog, CreateOrgRequestPolicy, OrgRequestPolicy, UpdateOrgRequestPolicy},
+    services::Services,
+};
+
+fn get_services(state: &AppState) -> Result<&Services, AdminError> {
+    state.services.as_ref().ok_or(AdminError::ServicesRequired)
+}
+
+// ============================================================================
+// Request / Response Types
+// ============================================================================
+
+/// List of request policies, highest priority first
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct OrgRequestPolicyListResponse {
+    /// Request policies
+    pub data: Vec<OrgRequestPolicy>,
+}
+
+/// Caller attributes for request policy simulation. Omitted IDs are unset.
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct SimulateRequestPolicySubject {
+    #[serde(default)]
+    pub user_id: Option<Uuid>,
+    #[serde(default)]
+    pub api_key_id: Option<Uuid>,
+    #[serde(default)]
+    pub team_id: Option<Uuid>,
+    #[serde(default)]
+    pub project_id: Option<Uuid>,
+    #[serde(default)]
+    pub service_account_id: Option<Uuid>,
+}
+
+/// Request to simulate request policy evaluation
+#[derive(Debug, Deserialize, Validate)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct SimulateRequestPoliciesRequest {
+    /// Sample request body, as sent to the endpoint
+    #[cfg_attr(feature = "utoipa", schema(value_type = Object))]
+    pub body: serde_json::Value,
+    /// Endpoint the request targets (defaults to `chat_completions`)
+    #[serde(default)]
+    #[cfg_attr(feature = "utoipa", schema(value_type = Option<String>))]
+    pub endpoint: Option<TransformEndpoint>,
+    /// Caller to simulate
+    #[serde(default)]
+    pub subject: SimulateRequestPolicySubject,
+    /// Unsaved policy to evaluate alongside the org's saved policies
+    #[serde(default)]
+    #[validate(nested)]
+    pub policy: Option<CreateOrgRequestPolicy>,
+}
+
+/// Result of a request policy simulation
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct SimulateRequestPoliciesResponse {
+    /// Whether request policies are enforced on this gateway
+    pub enforced: bool,
+    /// Decision and per-policy trace
+    pub evaluation: RequestPolicyEvaluation,
+    /// Request body after `modify` policies were applied
+    #[cfg_attr(feature = "utoipa", schema(value_type = Object))]
+    pub body: serde_json::Value,
+}
+
+// ============================================================================
+// CRUD Endpoints
+// ============================================================================
+
+/// List request policies for an organization
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/organizations/{org_slug}/request-policies",
+    tag = "request-policies",
+    operation_id = "org_request_policy_list",
+    params(("org_slug" = String, Path, description = "Organization slug")),
+    responses(
+        (status = 200, description = "List of request policies", body = OrgRequestPolicyListResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.request_policies.list", skip(state, authz), fields(%org_slug))]
+pub async fn list(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(org_slug): Path<String>,
+) -> Result<Json<OrgRequestPolicyListResponse>, AdminError> {
+    let services = get_services(&state)?;
+
+    let org = services
+        .organizations
+        .get_by_slug(&org_slug)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("Organization '{}' not found", org_slug)))?;
+
+    authz.require(
+        "request_policy",
+        "list",
+        None,
+        Some(&org.id.to_string()),
+        None,
+        None,
+    )?;
+
+    let data = services.org_request_policies.list_by_org(org.id).await?;
+
+    Ok(Json(OrgRequestPolicyListResponse { data }))
+}
+
+/// Create a request policy for an organization
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/organizations/{org_slug}/request-policies",
+    tag = "request-policies",
+    operation_id = "org_request_policy_create",
+    params(("org_slug" = String, Path, description = "Organization slug")),
+    request_body = CreateOrgRequestPolicy,
+    responses(
+        (status = 201, description = "Request policy created", body = OrgRequestPolicy),
+        (status = 400, description = "Invalid CEL expression or modifications", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found", body = crate::openapi::ErrorResponse),
+        (status = 409, description = "Policy with same name already exists", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.request_policies.create", skip(state, admin_auth, authz, input), fields(%org_slug))]
+pub async fn create(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path(org_slug): Path<String>,
+    Valid(Json(input)): Valid<Json<CreateOrgRequestPolicy>>,
+) -> Result<(StatusCode, Json<OrgRequestPolicy>), AdminError> {
+    let services = get_services(&state)?;
+    let actor = AuditActor::from(&admin_auth);
+
+    let org = services
+        .organizations
+        .get_by_slug(&org_slug)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("Organization '{}' not found", org_slug)))?;
+
+    authz.require(
+        "request_policy",
+        "create",
+        None,
+        Some(&org.id.to_string()),
+        None,
+        None,
+    )?;
+
+    let max_policies = state.config.limits.resource_limits.max_policies_per_org;
+    if max_policies > 0 {
+        let policy_count = services.org_request_policies.count_by_org(org.id).await?;
+        if policy_count >= max_policies as i64 {
+            return Err(AdminError::Conflict(format!(
+                "Organization has reached the maximum number of request policies ({})",
+                max_policies
+            )));
+        }
+    }
+
+    let policy = services.org_request_policies.create(org.id, input).await?;
+
+    services
+        .org_request_policies
+        .invalidate_cache(org.id, state.request_policies.as_deref())
+        .await;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "request_policy.create".to_string(),
+            resource_type: "request_policy".to_string(),
+            resource_id: policy.id,
+            org_id: Some(org.id),
+            project_id: None,
+            details: json!({
+                "name": policy.name,
+                "action": policy.action.to_string(),
+                "priority": policy.priority,
+                "enabled": policy.enabled,
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok((StatusCode::CREATED, Json(policy)))
+}
+
+/// Get a request policy by ID
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/organizations/{org_slug}/request-policies/{policy_id}",
+    tag = "request-policies",
+    operation_id = "org_request_policy_get",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("policy_id" = Uuid, Path, description = "Policy ID"),
+    ),
+    responses(
+        (status = 200, description = "Request policy found", body = OrgRequestPolicy),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization or policy not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.request_policies.get", skip(state, authz), fields(%org_slug, %policy_id))]
+pub async fn get(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path((org_slug, policy_id)): Path<(String, Uuid)>,
+) -> Result<Json<OrgRequestPolicy>, AdminError> {
+    let services = get_services(&state)?;
+    let (org_id, policy) = get_org_policy(services, &org_slug, policy_id).await?;
+
+    authz.require(
+        "request_policy",
+        "read",
+        Some(&policy_id.to_string()),
+        Some(&org_id.to_string()),
+        None,
+        None,
+    )?;
+
+    Ok(Json(policy))
+}
+
+/// Update a request policy
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    patch,
+    path = "/admin/v1/organizations/{org_slug}/request-policies/{policy_id}",
+    tag = "request-policies",
+    operation_id = "org_request_policy_update",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("policy_id" = Uuid, Path, description = "Policy ID"),
+    ),
+    request_body = UpdateOrgRequestPolicy,
+    responses(
+        (status = 200, description = "Request policy updated", body = OrgRequestPolicy),
+        (status = 400, description = "Invalid CEL expression or modifications", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization or policy not found", body = crate::openapi::ErrorResponse),
+        (status = 409, description = "Policy with same name already exists", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.request_policies.update", skip(state, admin_auth, authz, input), fields(%org_slug, %policy_id))]
+pub async fn update(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path((org_slug, policy_id)): Path<(String, Uuid)>,
+    Valid(Json(input)): Valid<Json<UpdateOrgRequestPolicy>>,
+) -> Result<Json<OrgRequestPolicy>, AdminError> {
+    let services = get_services(&state)?;
+    let actor = AuditActor::from(&admin_auth);
+    let (org_id, _) = get_org_policy(services, &org_slug, policy_id).await?;
+
+    authz.require(
+        "request_policy",
+        "update",
+        Some(&policy_id.to_string()),
+        Some(&org_id.to_string()),
+        None,
+        None,
+    )?;
+
+    let details = json!({
+        "name": input.name,
+        "action": input.action.map(|a| a.to_string()),
+        "priority": input.priority,
+        "enabled": input.enabled,
+        "condition_changed": input.condition.is_some(),
+        "modifications_changed": input.modifications.is_some(),
+    });
+    let updated = services
+        .org_request_policies
+        .update(policy_id, input)
+        .await?;
+
+    services
+        .org_request_policies
+        .invalidate_cache(org_id, state.request_policies.as_deref())
+        .await;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "request_policy.update".to_string(),
+            resource_type: "request_policy".to_string(),
+            resource_id: policy_id,
+            org_id: Some(org_id),
+            project_id: None,
+            details,
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(Json(updated))
+}
+
+/// Delete a request policy
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    delete,
+    path = "/admin/v1/organizations/{org_slug}/request-policies/{policy_id}",
+    tag = "request-policies",
+    operation_id = "org_request_policy_delete",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("policy_id" = Uuid, Path, description = "Policy ID"),
+    ),
+    responses(
+        (status = 200, description = "Request policy deleted"),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization or policy not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.request_policies.delete", skip(state, admin_auth, authz), fields(%org_slug, %policy_id))]
+pub async fn delete(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path((org_slug, policy_id)): Path<(String, Uuid)>,
+) -> Result<Json<()>, AdminError> {
+    let services = get_services(&state)?;
+    let actor = AuditActor::from(&admin_auth);
+    let (org_id, existing) = get_org_policy(services, &org_slug, policy_id).await?;
+
+    authz.require(
+        "request_policy",
+        "delete",
+        Some(&policy_id.to_string()),
+        Some(&org_id.to_string()),
+        None,
+        None,
+    )?;
+
+    services.org_request_policies.delete(policy_id).await?;
+
+    services
+        .org_request_policies
+        .invalidate_cache(org_id, state.request_policies.as_deref())
+        .await;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "request_policy.delete".to_string(),
+            resource_type: "request_policy".to_string(),
+            resource_id: policy_id,
+            org_id: Some(org_id),
+            project_id: None,
+            details: json!({
+                "name": existing.name,
+                "action": existing.action.to_string(),
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(Json(()))
+}
+
+/// Simulate request policy evaluation
+///
+/// Evaluates the org's enabled policies, plus an optional unsaved policy,
+/// against a sample request body without sending anything to a provider.
+/// Returns the decision, a per-policy trace and the body after any `modify`
+/// policies were applied.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/organizations/{org_slug}/request-policies/simulate",
+    tag = "request-policies",
+    operation_id = "org_request_policy_simulate",
+    params(("org_slug" = String, Path, description = "Organization slug")),
+    request_body = SimulateRequestPoliciesRequest,
+    responses(
+        (status = 200, description = "Simulation result", body = SimulateRequestPoliciesResponse),
+        (status = 400, description = "Invalid request body or unsaved policy", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.request_policies.simulate", skip(state, authz, input), fields(%org_slug))]
+pub async fn simulate(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(org_slug): Path<String>,
+    Valid(Json(input)): Valid<Json<SimulateRequestPoliciesRequest>>,
+) -> Result<Json<SimulateRequestPoliciesResponse>, AdminError> {
+    let services = get_services(&state)?;
+
+    let org = services
+        .organizations
+        .get_by_slug(&org_slug)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("Organization '{}' not found", org_slug)))?;
+
+    authz.require(
+        "request_policy",
+        "read",
+        None,
+        Some(&org.id.to_string()),
+        None,
+        None,
+    )?;
+
+    if !input.body.is_object() {
+        return Err(AdminError::Validation(
+            "body must be a JSON object".to_string(),
+        ));
+    }
+
+    let mut policies = services
+        .org_request_policies
+        .list_enabled_by_org(org.id)
+        .await?;
+    if let Some(draft) = input.policy {
+        services.org_request_policies.validate(&draft)?;
+        let now = chrono::Utc::now();
+        policies.push(OrgRequestPolicy {
+            id: Uuid::nil(),
+            org_id: org.id,
+            name: draft.name,
+            description: draft.description,
+            condition: draft.condition,
+            action: draft.action,
+            modifications: draft.modifications,
+            message: draft.message,
+            priority: draft.priority,
+            enabled: true,
+            created_at: now,
+            updated_at: now,
+        });
+    }
+
+    let config = &state.config.features.request_policies;
+    let compiled = compile_request_policies(policies, state.config.auth.rbac.max_expression_length);
+    let subject = RequestPolicySubject {
+        org_id: org.id,
+        user_id: input.subject.user_id,
+        api_key_id: input.subject.api_key_id,
+        team_id: input.subject.team_id,
+        project_id: input.subject.project_id,
+        service_account_id: input.subject.service_account_id,
+    };
+    let endpoint = input.endpoint.unwrap_or(TransformEndpoint::ChatCompletions);
+    let mut body = input.body;
+    let evaluation = evaluate_request_policies(
+        &compiled,
+        endpoint.as_str(),
+        &subject,
+        &TimeContext::now(),
+        &mut body,
+        config.fail_on_evaluation_error,
+    );
+
+    Ok(Json(SimulateRequestPoliciesResponse {
+        enforced: state.request_policies.is_some(),
+        evaluation,
+        body,
+    }))
+}
+
+/// Look up an org by slug and one of its policies, treating a policy from
+/// another org as not found.
+async fn get_org_policy(
+    services: &Services,
+    org_slug: &str,
+    policy_id: Uuid,
+) -> Result<(Uuid, OrgRequestPolicy), AdminError> {
+    let org = services
+        .organizations
+        .get_by_slug(org_slug)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("Organization '{}' not found", org_slug)))?;
+
+    let policy = services
+        .org_request_policies
+        .get_by_id(policy_id)
+        .await?
+        .filter(|p| p.org_id == org.id)
+        .ok_or_else(|| {
+            AdminError::NotFound(format!(
+                "Request policy '{}' not found in organization '{}'",
+                policy_id, org_slug
+            ))
+        })?;
+
+    Ok((org.id, policy))
+}
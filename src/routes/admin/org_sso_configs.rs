@@ -3,6 +3,8 @@
 //! Each organization can have at most one SSO configuration, enabling IT admins
 //! to configure their own identity provider (OIDC or SAML) via the Admin UI.
 
+use std::collections::HashMap;
+
 #[cfg(feature = "saml")]
 use axum::response::{IntoResponse, Response};
 use axum::{
@@ -11,10 +13,9 @@ use axum::{
     http::StatusCode,
 };
 use axum_valid::Valid;
-#[cfg(feature = "saml")]
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-#[cfg(feature = "saml")]
+use uuid::Uuid;
 use validator::Validate;
 
 use super::{AuditActor, error::AdminError};
@@ -22,7 +23,8 @@ use crate::{
     AppState,
     middleware::{AdminAuth, AuthzContext, ClientInfo},
     models::{
-        CreateAuditLog, CreateOrgSsoConfig, OrgSsoConfig, SsoProviderType, UpdateOrgSsoConfig,
+        CreateAuditLog, CreateOrgSsoConfig, OrgSsoConfig, Organization, ResolvedRoleRule,
+        SsoProviderType, SsoRoleRule, SsoRoleRules, UpdateOrgSsoConfig, resolve_role_rules,
     },
     secrets::SecretManager,
     services::Services,
@@ -713,6 +715,257 @@ pub async fn delete(
     Ok(Json(()))
 }
 
+// ============================================================================
+// Claim-based role rules
+// ============================================================================
+
+/// Request to dry-run role rules against a set of claims.
+#[derive(Debug, Deserialize, Validate)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct EvaluateRoleRulesRequest {
+    /// Claims as they would appear in the ID token (or SAML attributes)
+    pub claims: HashMap<String, serde_json::Value>,
+    /// Draft rules to evaluate instead of the saved rules
+    #[serde(default)]
+    #[validate(length(max = 200))]
+    pub rules: Option<Vec<SsoRoleRule>>,
+}
+
+/// Result of a role rules dry run.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct EvaluateRoleRulesResponse {
+    /// Names of every rule whose conditions matched, in evaluation order
+    pub matched_rules: Vec<String>,
+    /// Memberships a user with these claims would receive on first login
+    pub memberships: Vec<ResolvedRoleRule>,
+}
+
+/// Look up an organization and its SSO config, or 404.
+async fn org_with_sso_config(
+    services: &Services,
+    org_slug: &str,
+) -> Result<(Organization, OrgSsoConfig), AdminError> {
+    let org = services
+        .organizations
+        .get_by_slug(org_slug)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("Organization '{}' not found", org_slug)))?;
+
+    let config = services
+        .org_sso_configs
+        .get_by_org_id(org.id)
+        .await?
+        .ok_or_else(|| {
+            AdminError::NotFound(format!(
+                "SSO config not found for organization '{}'",
+                org_slug
+            ))
+        })?;
+
+    Ok((org, config))
+}
+
+/// Check that every rule targets a team or project in `org_id`.
+async fn validate_rule_targets(
+    services: &Services,
+    org_id: Uuid,
+    rules: &[SsoRoleRule],
+) -> Result<(), AdminError> {
+    for rule in rules {
+        if let Some(team_id) = rule.team_id {
+            let team = services.teams.get_by_id(team_id).await?;
+            if team.is_none_or(|t| t.org_id != org_id) {
+                return Err(AdminError::Validation(format!(
+                    "Rule '{}': team '{}' not found in this organization",
+                    rule.name, team_id
+                )));
+            }
+        }
+        if let Some(project_id) = rule.project_id {
+            let project = services.projects.get_by_id(project_id).await?;
+            if project.is_none_or(|p| p.org_id != org_id) {
+                return Err(AdminError::Validation(format!(
+                    "Rule '{}': project '{}' not found in this organization",
+                    rule.name, project_id
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Get the claim-based role rules for an organization
+///
+/// Role rules grant team and project memberships during JIT provisioning
+/// based on arbitrary identity claims, complementing SSO group mappings.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/organizations/{org_slug}/sso-config/role-rules",
+    tag = "sso",
+    operation_id = "org_sso_config_get_role_rules",
+    params(("org_slug" = String, Path, description = "Organization slug")),
+    responses(
+        (status = 200, description = "Role rules", body = SsoRoleRules),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization or SSO config not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.org_sso_configs.get_role_rules", skip(state, authz), fields(%org_slug))]
+pub async fn get_role_rules(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(org_slug): Path<String>,
+) -> Result<Json<SsoRoleRules>, AdminError> {
+    let services = get_services(&state)?;
+    let (org, config) = org_with_sso_config(services, &org_slug).await?;
+
+    authz.require(
+        "org_sso_config",
+        "read",
+        Some(&config.id.to_string()),
+        Some(&org.id.to_string()),
+        None,
+        None,
+    )?;
+
+    let rules = services.org_sso_configs.get_role_rules(org.id).await?;
+    Ok(Json(SsoRoleRules { rules }))
+}
+
+/// Replace the claim-based role rules for an organization
+///
+/// Rules are evaluated in order on a user's first SSO login. A rule matches
+/// when all of its conditions hold, and grants its role in the rule's team or
+/// project. When several matching rules target the same team or project, the
+/// highest priority wins.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    put,
+    path = "/admin/v1/organizations/{org_slug}/sso-config/role-rules",
+    tag = "sso",
+    operation_id = "org_sso_config_set_role_rules",
+    params(("org_slug" = String, Path, description = "Organization slug")),
+    request_body = SsoRoleRules,
+    responses(
+        (status = 200, description = "Role rules saved", body = SsoRoleRules),
+        (status = 400, description = "Invalid rules", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization or SSO config not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.org_sso_configs.set_role_rules", skip(state, admin_auth, authz, input), fields(%org_slug))]
+pub async fn set_role_rules(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path(org_slug): Path<String>,
+    Valid(Json(input)): Valid<Json<SsoRoleRules>>,
+) -> Result<Json<SsoRoleRules>, AdminError> {
+    let services = get_services(&state)?;
+    let actor = AuditActor::from(&admin_auth);
+    let (org, config) = org_with_sso_config(services, &org_slug).await?;
+
+    authz.require(
+        "org_sso_config",
+        "update",
+        Some(&config.id.to_string()),
+        Some(&org.id.to_string()),
+        None,
+        None,
+    )?;
+
+    validate_rule_targets(services, org.id, &input.rules).await?;
+
+    services
+        .org_sso_configs
+        .set_role_rules(org.id, &input.rules)
+        .await?;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "org_sso_config.role_rules_update".to_string(),
+            resource_type: "org_sso_config".to_string(),
+            resource_id: config.id,
+            org_id: Some(org.id),
+            project_id: None,
+            details: json!({
+                "rule_count": input.rules.len(),
+                "rules": input.rules.iter().map(|r| r.name.as_str()).collect::<Vec<_>>(),
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(Json(input))
+}
+
+/// Dry-run claim-based role rules
+///
+/// Evaluates the saved rules (or draft `rules`, if provided) against the given
+/// claims and returns the memberships a user with those claims would receive.
+/// Nothing is saved and no memberships are changed.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/organizations/{org_slug}/sso-config/role-rules/evaluate",
+    tag = "sso",
+    operation_id = "org_sso_config_evaluate_role_rules",
+    params(("org_slug" = String, Path, description = "Organization slug")),
+    request_body = EvaluateRoleRulesRequest,
+    responses(
+        (status = 200, description = "Evaluation results", body = EvaluateRoleRulesResponse),
+        (status = 400, description = "Invalid rules", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization or SSO config not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.org_sso_configs.evaluate_role_rules", skip(state, authz, input), fields(%org_slug))]
+pub async fn evaluate_role_rules(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(org_slug): Path<String>,
+    Valid(Json(input)): Valid<Json<EvaluateRoleRulesRequest>>,
+) -> Result<Json<EvaluateRoleRulesResponse>, AdminError> {
+    let services = get_services(&state)?;
+    let (org, config) = org_with_sso_config(services, &org_slug).await?;
+
+    authz.require(
+        "org_sso_config",
+        "read",
+        Some(&config.id.to_string()),
+        Some(&org.id.to_string()),
+        None,
+        None,
+    )?;
+
+    let rules = match input.rules {
+        Some(rules) => {
+            for rule in &rules {
+                rule.validate()
+                    .map_err(|e| AdminError::Validation(format!("Rule '{}': {}", rule.name, e)))?;
+            }
+            rules
+        }
+        None => services.org_sso_configs.get_role_rules(org.id).await?,
+    };
+
+    let matched_rules = rules
+        .iter()
+        .filter(|r| r.matches(&input.claims))
+        .map(|r| r.name.clone())
+        .collect();
+    let memberships = resolve_role_rules(&rules, &input.claims);
+
+    Ok(Json(EvaluateRoleRulesResponse {
+        matched_rules,
+        memberships,
+    }))
+}
+
 // ============================================================================
 // SAML Metadata Endpoints
 // ============================================================================
@@ -6,13 +6,20 @@ use axum::{
 use axum_valid::Valid;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use validator::Validate;
 
 use super::{AuditActor, error::AdminError};
 use crate::{
     AppState,
+    cache::CacheKeys,
+    config::EntitlementsConfig,
     db::{Cursor, CursorDirection, ListParams},
     middleware::{AdminAuth, AuthzContext, ClientInfo},
-    models::{CreateAuditLog, CreateOrganization, Organization, UpdateOrganization},
+    models::{
+        CreateAuditLog, CreateOrganization, EntitlementUsage, Entitlements, LabelSelector,
+        ModelAccessPolicy, ModelDegradationPolicy, OrgEntitlements, OrgNetworkPolicy, Organization,
+        ParameterGovernance, SetLabels, UpdateOrganization, validate_labels,
+    },
     openapi::PaginationMeta,
     services::{OrganizationService, Services},
 };
@@ -46,6 +53,7 @@ impl From<ListQuery> for ListParams {
             direction: CursorDirection::Forward,
             sort_order: Default::default(),
             include_deleted: q.include_deleted.unwrap_or(false),
+            labels: Default::default(),
         }
         .clamp()
     }
@@ -79,11 +87,30 @@ impl ListQuery {
             direction,
             sort_order: Default::default(),
             include_deleted: self.include_deleted.unwrap_or(false),
+            labels: Default::default(),
         }
         .clamp())
     }
 }
 
+/// Label selector accepted by the list endpoints of labelled resources.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema, utoipa::IntoParams))]
+pub struct LabelQuery {
+    /// Only return resources with all of these labels, e.g. `env=prod,tier=web`
+    pub label: Option<String>,
+}
+
+impl LabelQuery {
+    /// Add the selector to `params`, returning an error for an invalid one.
+    pub fn apply(self, mut params: ListParams) -> Result<ListParams, AdminError> {
+        if let Some(selector) = &self.label {
+            params.labels = LabelSelector::parse(selector).map_err(AdminError::BadRequest)?;
+        }
+        Ok(params)
+    }
+}
+
 /// Paginated list of organizations
 #[derive(Debug, Serialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
@@ -197,10 +224,10 @@ pub async fn get(
     path = "/admin/v1/organizations",
     tag = "organizations",
     operation_id = "organization_list",
-    params(ListQuery),
+    params(ListQuery, LabelQuery),
     responses(
         (status = 200, description = "List of organizations", body = OrganizationListResponse),
-        (status = 400, description = "Invalid cursor or direction", body = crate::openapi::ErrorResponse),
+        (status = 400, description = "Invalid cursor, direction or label selector", body = crate::openapi::ErrorResponse),
         (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
     )
 ))]
@@ -208,12 +235,13 @@ pub async fn list(
     State(state): State<AppState>,
     Extension(authz): Extension<AuthzContext>,
     Query(query): Query<ListQuery>,
+    Query(labels): Query<LabelQuery>,
 ) -> Result<Json<OrganizationListResponse>, AdminError> {
     authz.require("organization", "list", None, None, None, None)?;
 
     let service = get_service(&state)?;
     let limit = query.limit.unwrap_or(100);
-    let params = query.try_into_with_cursor()?;
+    let params = labels.apply(query.try_into_with_cursor()?)?;
 
     let result = service.list(params).await?;
 
@@ -271,13 +299,50 @@ pub async fn update(
         None,
     )?;
 
+    if let Some(Some(policy)) = &input.data_residency
+        && policy.allowed_regions.iter().all(|r| r.trim().is_empty())
+    {
+        return Err(AdminError::Validation(
+            "data_residency.allowed_regions must contain at least one region".to_string(),
+        ));
+    }
+
+    if let Some(Some(defaults)) = &input.request_defaults {
+        defaults
+            .validate()
+            .map_err(|e| AdminError::Validation(format!("request_defaults: {e}")))?;
+    }
+
+    if let Some(Some(settings)) = &input.conversation_summaries {
+        settings
+            .validate()
+            .map_err(|e| AdminError::Validation(format!("conversation_summaries: {e}")))?;
+    }
+
     // Capture changes for audit log
     let changes = json!({
         "name": input.name,
+        "data_residency": input.data_residency,
+        "request_defaults": input.request_defaults,
+        "conversation_summaries": input.conversation_summaries,
     });
 
+    let residency_changed = input.data_residency.is_some();
+    let defaults_changed = input.request_defaults.is_some();
+
     let updated = services.organizations.update(org.id, input).await?;
 
+    // The data plane caches the residency policy and request defaults per
+    // org; drop them so the change takes effect on the next request.
+    if let Some(cache) = &state.cache {
+        if residency_changed {
+            let _ = cache.delete(&CacheKeys::org_data_residency(org.id)).await;
+        }
+        if defaults_changed {
+            let _ = cache.delete(&CacheKeys::org_request_defaults(org.id)).await;
+        }
+    }
+
     // Log audit event
     let _ = services
         .audit_logs
@@ -301,6 +366,66 @@ pub async fn update(
     Ok(Json(updated))
 }
 
+/// Set an organization's labels
+///
+/// Replaces all of the organization's labels. Send an empty object to
+/// remove them.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    put,
+    path = "/admin/v1/organizations/{slug}/labels",
+    tag = "organizations",
+    operation_id = "organization_set_labels",
+    params(("slug" = String, Path, description = "Organization slug")),
+    request_body = SetLabels,
+    responses(
+        (status = 200, description = "Labels saved", body = Organization),
+        (status = 400, description = "Invalid labels", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn set_labels(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path(slug): Path<String>,
+    Json(input): Json<SetLabels>,
+) -> Result<Json<Organization>, AdminError> {
+    let services = get_services(&state)?;
+    let actor = AuditActor::from(&admin_auth);
+    let org = authorized_org(services, &authz, &slug, "update").await?;
+
+    validate_labels(&input.labels).map_err(AdminError::Validation)?;
+
+    let updated = services
+        .organizations
+        .set_labels(org.id, &input.labels)
+        .await?;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "organization.labels_update".to_string(),
+            resource_type: "organization".to_string(),
+            resource_id: org.id,
+            org_id: Some(org.id),
+            project_id: None,
+            details: json!({
+                "slug": org.slug,
+                "previous": org.labels,
+                "labels": input.labels,
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(Json(updated))
+}
+
 /// Delete an organization
 #[cfg_attr(feature = "utoipa", utoipa::path(
     delete,
@@ -377,3 +502,1002 @@ pub async fn delete(
 
     Ok(Json(()))
 }
+
+/// Look up an organization by slug and check `action` on it.
+async fn authorized_org(
+    services: &Services,
+    authz: &AuthzContext,
+    slug: &str,
+    action: &str,
+) -> Result<Organization, AdminError> {
+    let org = services
+        .organizations
+        .get_by_slug(slug)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("Organization '{}' not found", slug)))?;
+
+    authz.require(
+        "organization",
+        action,
+        Some(&org.id.to_string()),
+        Some(&org.id.to_string()),
+        None,
+        None,
+    )?;
+    Ok(org)
+}
+
+/// Get an organization's network policy
+///
+/// The network policy restricts which IP ranges the organization's API keys
+/// may be used from.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/organizations/{slug}/network-policy",
+    tag = "organizations",
+    operation_id = "organization_get_network_policy",
+    params(("slug" = String, Path, description = "Organization slug")),
+    responses(
+        (status = 200, description = "Network policy", body = OrgNetworkPolicy),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found or no policy set", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn get_network_policy(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(slug): Path<String>,
+) -> Result<Json<OrgNetworkPolicy>, AdminError> {
+    let services = get_services(&state)?;
+    let org = authorized_org(services, &authz, &slug, "read").await?;
+
+    org.network_policy.map(Json).ok_or_else(|| {
+        AdminError::NotFound(format!("Organization '{}' has no network policy", org.slug))
+    })
+}
+
+/// Set an organization's network policy
+///
+/// Requests made with any of the organization's API keys (organization,
+/// team, project and service account keys, and tokens exchanged from them)
+/// are rejected with `403` unless the client IP falls within `ip_allowlist`.
+/// The policy applies in addition to each key's own IP allowlist. Denials
+/// are recorded in the audit log as `network_policy.denied`.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    put,
+    path = "/admin/v1/organizations/{slug}/network-policy",
+    tag = "organizations",
+    operation_id = "organization_set_network_policy",
+    params(("slug" = String, Path, description = "Organization slug")),
+    request_body = OrgNetworkPolicy,
+    responses(
+        (status = 200, description = "Network policy saved", body = OrgNetworkPolicy),
+        (status = 400, description = "Invalid policy", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn set_network_policy(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path(slug): Path<String>,
+    Json(policy): Json<OrgNetworkPolicy>,
+) -> Result<Json<OrgNetworkPolicy>, AdminError> {
+    let services = get_services(&state)?;
+    let actor = AuditActor::from(&admin_auth);
+    let org = authorized_org(services, &authz, &slug, "update").await?;
+
+    policy.validate().map_err(AdminError::Validation)?;
+
+    services
+        .organizations
+        .set_network_policy(org.id, Some(&policy))
+        .await?;
+    invalidate_network_policy(&state, &org).await;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "organization.network_policy_update".to_string(),
+            resource_type: "organization".to_string(),
+            resource_id: org.id,
+            org_id: Some(org.id),
+            project_id: None,
+            details: json!({
+                "slug": org.slug,
+                "previous": org.network_policy,
+                "ip_allowlist": policy.ip_allowlist,
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(Json(policy))
+}
+
+/// Remove an organization's network policy
+///
+/// The organization's API keys become usable from any IP allowed by their
+/// own allowlists.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    delete,
+    path = "/admin/v1/organizations/{slug}/network-policy",
+    tag = "organizations",
+    operation_id = "organization_delete_network_policy",
+    params(("slug" = String, Path, description = "Organization slug")),
+    responses(
+        (status = 200, description = "Network policy removed"),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found or no policy set", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn delete_network_policy(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path(slug): Path<String>,
+) -> Result<Json<()>, AdminError> {
+    let services = get_services(&state)?;
+    let actor = AuditActor::from(&admin_auth);
+    let org = authorized_org(services, &authz, &slug, "update").await?;
+
+    if org.network_policy.is_none() {
+        return Err(AdminError::NotFound(format!(
+            "Organization '{}' has no network policy",
+            org.slug
+        )));
+    }
+
+    services
+        .organizations
+        .set_network_policy(org.id, None)
+        .await?;
+    invalidate_network_policy(&state, &org).await;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "organization.network_policy_delete".to_string(),
+            resource_type: "organization".to_string(),
+            resource_id: org.id,
+            org_id: Some(org.id),
+            project_id: None,
+            details: json!({
+                "slug": org.slug,
+                "previous": org.network_policy,
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(Json(()))
+}
+
+/// Drop the data plane's cached copy of an org's network policy so a change
+/// takes effect on the next request.
+async fn invalidate_network_policy(state: &AppState, org: &Organization) {
+    if let Some(cache) = &state.cache {
+        let _ = cache.delete(&CacheKeys::org_network_policy(org.id)).await;
+    }
+}
+
+/// Get an organization's parameter governance rules
+///
+/// Parameter governance bounds the request parameters (`max_tokens`,
+/// `temperature`, forbidden fields) the organization's callers may send.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/organizations/{slug}/parameter-governance",
+    tag = "organizations",
+    operation_id = "organization_get_parameter_governance",
+    params(("slug" = String, Path, description = "Organization slug")),
+    responses(
+        (status = 200, description = "Parameter governance rules", body = ParameterGovernance),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found or no rules set", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn get_parameter_governance(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(slug): Path<String>,
+) -> Result<Json<ParameterGovernance>, AdminError> {
+    let services = get_services(&state)?;
+    let org = authorized_org(services, &authz, &slug, "read").await?;
+
+    org.parameter_governance.map(Json).ok_or_else(|| {
+        AdminError::NotFound(format!(
+            "Organization '{}' has no parameter governance",
+            org.slug
+        ))
+    })
+}
+
+/// Set an organization's parameter governance rules
+///
+/// Rules apply to chat completions, completions, responses and embeddings
+/// requests from any of the organization's callers, before project rules.
+/// A `clamp` rule brings out-of-bounds parameters within bounds and
+/// forwards the request; a `reject` rule fails it with `400`.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    put,
+    path = "/admin/v1/organizations/{slug}/parameter-governance",
+    tag = "organizations",
+    operation_id = "organization_set_parameter_governance",
+    params(("slug" = String, Path, description = "Organization slug")),
+    request_body = ParameterGovernance,
+    responses(
+        (status = 200, description = "Parameter governance saved", body = ParameterGovernance),
+        (status = 400, description = "Invalid rules", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn set_parameter_governance(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path(slug): Path<String>,
+    Json(governance): Json<ParameterGovernance>,
+) -> Result<Json<ParameterGovernance>, AdminError> {
+    let services = get_services(&state)?;
+    let actor = AuditActor::from(&admin_auth);
+    let org = authorized_org(services, &authz, &slug, "update").await?;
+
+    governance.validate().map_err(AdminError::Validation)?;
+
+    services
+        .organizations
+        .set_parameter_governance(org.id, Some(&governance))
+        .await?;
+    invalidate_parameter_governance(&state, &org).await;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "organization.parameter_governance_update".to_string(),
+            resource_type: "organization".to_string(),
+            resource_id: org.id,
+            org_id: Some(org.id),
+            project_id: None,
+            details: json!({
+                "slug": org.slug,
+                "previous": org.parameter_governance,
+                "rules": governance.rules,
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(Json(governance))
+}
+
+/// Remove an organization's parameter governance rules
+///
+/// Project rules still apply.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    delete,
+    path = "/admin/v1/organizations/{slug}/parameter-governance",
+    tag = "organizations",
+    operation_id = "organization_delete_parameter_governance",
+    params(("slug" = String, Path, description = "Organization slug")),
+    responses(
+        (status = 200, description = "Parameter governance removed"),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found or no rules set", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn delete_parameter_governance(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path(slug): Path<String>,
+) -> Result<Json<()>, AdminError> {
+    let services = get_services(&state)?;
+    let actor = AuditActor::from(&admin_auth);
+    let org = authorized_org(services, &authz, &slug, "update").await?;
+
+    if org.parameter_governance.is_none() {
+        return Err(AdminError::NotFound(format!(
+            "Organization '{}' has no parameter governance",
+            org.slug
+        )));
+    }
+
+    services
+        .organizations
+        .set_parameter_governance(org.id, None)
+        .await?;
+    invalidate_parameter_governance(&state, &org).await;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "organization.parameter_governance_delete".to_string(),
+            resource_type: "organization".to_string(),
+            resource_id: org.id,
+            org_id: Some(org.id),
+            project_id: None,
+            details: json!({
+                "slug": org.slug,
+                "previous": org.parameter_governance,
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(Json(()))
+}
+
+async fn invalidate_parameter_governance(state: &AppState, org: &Organization) {
+    if let Some(cache) = &state.cache {
+        let _ = cache
+            .delete(&CacheKeys::org_parameter_governance(org.id))
+            .await;
+    }
+}
+
+/// Get an organization's model access policy
+///
+/// The model access policy limits which models and providers the
+/// organization's callers may use.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/organizations/{slug}/model-access",
+    tag = "organizations",
+    operation_id = "organization_get_model_access",
+    params(("slug" = String, Path, description = "Organization slug")),
+    responses(
+        (status = 200, description = "Model access policy", body = ModelAccessPolicy),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found or no policy set", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn get_model_access(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(slug): Path<String>,
+) -> Result<Json<ModelAccessPolicy>, AdminError> {
+    let services = get_services(&state)?;
+    let org = authorized_org(services, &authz, &slug, "read").await?;
+
+    org.model_access.map(Json).ok_or_else(|| {
+        AdminError::NotFound(format!(
+            "Organization '{}' has no model access policy",
+            org.slug
+        ))
+    })
+}
+
+/// Set an organization's model access policy
+///
+/// Requests from the organization's callers for a denied model or provider,
+/// or one outside a non-empty allow list, are rejected with `403` before
+/// routing. Denials are recorded in the audit log as `model_access.denied`.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    put,
+    path = "/admin/v1/organizations/{slug}/model-access",
+    tag = "organizations",
+    operation_id = "organization_set_model_access",
+    params(("slug" = String, Path, description = "Organization slug")),
+    request_body = ModelAccessPolicy,
+    responses(
+        (status = 200, description = "Model access policy saved", body = ModelAccessPolicy),
+        (status = 400, description = "Invalid policy", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn set_model_access(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path(slug): Path<String>,
+    Json(policy): Json<ModelAccessPolicy>,
+) -> Result<Json<ModelAccessPolicy>, AdminError> {
+    let services = get_services(&state)?;
+    let actor = AuditActor::from(&admin_auth);
+    let org = authorized_org(services, &authz, &slug, "update").await?;
+
+    policy.validate().map_err(AdminError::Validation)?;
+
+    services
+        .organizations
+        .set_model_access(org.id, Some(&policy))
+        .await?;
+    invalidate_model_access(&state, &org).await;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "organization.model_access_update".to_string(),
+            resource_type: "organization".to_string(),
+            resource_id: org.id,
+            org_id: Some(org.id),
+            project_id: None,
+            details: json!({
+                "slug": org.slug,
+                "previous": org.model_access,
+                "policy": policy,
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(Json(policy))
+}
+
+/// Remove an organization's model access policy
+///
+/// Project policies and API key model restrictions still apply.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    delete,
+    path = "/admin/v1/organizations/{slug}/model-access",
+    tag = "organizations",
+    operation_id = "organization_delete_model_access",
+    params(("slug" = String, Path, description = "Organization slug")),
+    responses(
+        (status = 200, description = "Model access policy removed"),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found or no policy set", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn delete_model_access(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path(slug): Path<String>,
+) -> Result<Json<()>, AdminError> {
+    let services = get_services(&state)?;
+    let actor = AuditActor::from(&admin_auth);
+    let org = authorized_org(services, &authz, &slug, "update").await?;
+
+    if org.model_access.is_none() {
+        return Err(AdminError::NotFound(format!(
+            "Organization '{}' has no model access policy",
+            org.slug
+        )));
+    }
+
+    services
+        .organizations
+        .set_model_access(org.id, None)
+        .await?;
+    invalidate_model_access(&state, &org).await;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "organization.model_access_delete".to_string(),
+            resource_type: "organization".to_string(),
+            resource_id: org.id,
+            org_id: Some(org.id),
+            project_id: None,
+            details: json!({
+                "slug": org.slug,
+                "previous": org.model_access,
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(Json(()))
+}
+
+async fn invalidate_model_access(state: &AppState, org: &Organization) {
+    if let Some(cache) = &state.cache {
+        let _ = cache.delete(&CacheKeys::org_model_access(org.id)).await;
+    }
+}
+
+/// Get an organization's model degradation policy
+///
+/// The model degradation policy opts the organization into downgrading
+/// premium models to cheaper ones when they're rate limited, over budget or
+/// unavailable.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/organizations/{slug}/model-degradation",
+    tag = "organizations",
+    operation_id = "organization_get_model_degradation",
+    params(("slug" = String, Path, description = "Organization slug")),
+    responses(
+        (status = 200, description = "Model degradation policy", body = ModelDegradationPolicy),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found or no policy set", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn get_model_degradation(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(slug): Path<String>,
+) -> Result<Json<ModelDegradationPolicy>, AdminError> {
+    let services = get_services(&state)?;
+    let org = authorized_org(services, &authz, &slug, "read").await?;
+
+    org.model_degradation.map(Json).ok_or_else(|| {
+        AdminError::NotFound(format!(
+            "Organization '{}' has no model degradation policy",
+            org.slug
+        ))
+    })
+}
+
+/// Set an organization's model degradation policy
+///
+/// Chat completion requests from the organization's callers (or only the
+/// listed API keys) for a model with a ladder are moved down it when one of
+/// the policy's triggers fires. Downgraded responses carry
+/// `X-Model-Degraded-From` and `X-Model-Degradation-Reason` headers.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    put,
+    path = "/admin/v1/organizations/{slug}/model-degradation",
+    tag = "organizations",
+    operation_id = "organization_set_model_degradation",
+    params(("slug" = String, Path, description = "Organization slug")),
+    request_body = ModelDegradationPolicy,
+    responses(
+        (status = 200, description = "Model degradation policy saved", body = ModelDegradationPolicy),
+        (status = 400, description = "Invalid policy", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn set_model_degradation(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path(slug): Path<String>,
+    Json(policy): Json<ModelDegradationPolicy>,
+) -> Result<Json<ModelDegradationPolicy>, AdminError> {
+    let services = get_services(&state)?;
+    let actor = AuditActor::from(&admin_auth);
+    let org = authorized_org(services, &authz, &slug, "update").await?;
+
+    policy.validate().map_err(AdminError::Validation)?;
+
+    services
+        .organizations
+        .set_model_degradation(org.id, Some(&policy))
+        .await?;
+    invalidate_model_degradation(&state, &org).await;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "organization.model_degradation_update".to_string(),
+            resource_type: "organization".to_string(),
+            resource_id: org.id,
+            org_id: Some(org.id),
+            project_id: None,
+            details: json!({
+                "slug": org.slug,
+                "previous": org.model_degradation,
+                "policy": policy,
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(Json(policy))
+}
+
+/// Remove an organization's model degradation policy
+///
+/// The organization's requests are no longer downgraded.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    delete,
+    path = "/admin/v1/organizations/{slug}/model-degradation",
+    tag = "organizations",
+    operation_id = "organization_delete_model_degradation",
+    params(("slug" = String, Path, description = "Organization slug")),
+    responses(
+        (status = 200, description = "Model degradation policy removed"),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found or no policy set", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn delete_model_degradation(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path(slug): Path<String>,
+) -> Result<Json<()>, AdminError> {
+    let services = get_services(&state)?;
+    let actor = AuditActor::from(&admin_auth);
+    let org = authorized_org(services, &authz, &slug, "update").await?;
+
+    if org.model_degradation.is_none() {
+        return Err(AdminError::NotFound(format!(
+            "Organization '{}' has no model degradation policy",
+            org.slug
+        )));
+    }
+
+    services
+        .organizations
+        .set_model_degradation(org.id, None)
+        .await?;
+    invalidate_model_degradation(&state, &org).await;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "organization.model_degradation_delete".to_string(),
+            resource_type: "organization".to_string(),
+            resource_id: org.id,
+            org_id: Some(org.id),
+            project_id: None,
+            details: json!({
+                "slug": org.slug,
+                "previous": org.model_degradation,
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(Json(()))
+}
+
+async fn invalidate_model_degradation(state: &AppState, org: &Organization) {
+    if let Some(cache) = &state.cache {
+        let _ = cache
+            .delete(&CacheKeys::org_model_degradation(org.id))
+            .await;
+    }
+}
+
+/// An organization's plan, overrides and usage.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct OrgEntitlementsResponse {
+    /// Plan and overrides assigned to the organization; absent when it's on
+    /// the default plan
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assigned: Option<OrgEntitlements>,
+    /// What the organization may use
+    pub effective: Entitlements,
+    /// Usage counted against the limits
+    pub usage: EntitlementUsage,
+}
+
+fn entitlements_config(state: &AppState) -> Result<&EntitlementsConfig, AdminError> {
+    let config = &state.config.features.entitlements;
+    if !config.enabled {
+        return Err(AdminError::NotConfigured(
+            "Entitlements are not enabled".to_string(),
+        ));
+    }
+    Ok(config)
+}
+
+/// Look up an organization and require permission to change its
+/// entitlements. This is a separate `entitlements` resource so that
+/// organization admins can't upgrade their own plan.
+async fn entitlements_org(
+    services: &Services,
+    authz: &AuthzContext,
+    slug: &str,
+) -> Result<Organization, AdminError> {
+    let org = services
+        .organizations
+        .get_by_slug(slug)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("Organization '{}' not found", slug)))?;
+
+    authz.require(
+        "entitlements",
+        "update",
+        Some(&org.id.to_string()),
+        Some(&org.id.to_string()),
+        None,
+        None,
+    )?;
+    Ok(org)
+}
+
+async fn entitlements_response(
+    services: &Services,
+    config: &EntitlementsConfig,
+    org: &Organization,
+) -> Result<OrgEntitlementsResponse, AdminError> {
+    Ok(OrgEntitlementsResponse {
+        assigned: org.entitlements.clone(),
+        effective: Entitlements::resolve(config, org.entitlements.as_ref()),
+        usage: services.entitlements.usage(org.id).await?,
+    })
+}
+
+/// Get an organization's entitlements
+///
+/// Returns the plan and overrides assigned to the organization, the
+/// entitlements they resolve to, and the projects and active API keys
+/// counted against its limits.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/organizations/{slug}/entitlements",
+    tag = "organizations",
+    operation_id = "organization_get_entitlements",
+    params(("slug" = String, Path, description = "Organization slug")),
+    responses(
+        (status = 200, description = "Organization entitlements", body = OrgEntitlementsResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "Entitlements are not enabled", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn get_entitlements(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(slug): Path<String>,
+) -> Result<Json<OrgEntitlementsResponse>, AdminError> {
+    let config = entitlements_config(&state)?;
+    let services = get_services(&state)?;
+    let org = authorized_org(services, &authz, &slug, "read").await?;
+
+    Ok(Json(entitlements_response(services, config, &org).await?))
+}
+
+/// Set an organization's entitlements
+///
+/// Assigns a plan and/or overrides individual features and limits. Fields
+/// left out come from the plan. Lowering a limit below current usage
+/// doesn't remove anything; it only blocks creating more.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    put,
+    path = "/admin/v1/organizations/{slug}/entitlements",
+    tag = "organizations",
+    operation_id = "organization_set_entitlements",
+    params(("slug" = String, Path, description = "Organization slug")),
+    request_body = OrgEntitlements,
+    responses(
+        (status = 200, description = "Entitlements saved", body = OrgEntitlementsResponse),
+        (status = 400, description = "Invalid entitlements", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "Entitlements are not enabled", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn set_entitlements(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path(slug): Path<String>,
+    Json(entitlements): Json<OrgEntitlements>,
+) -> Result<Json<OrgEntitlementsResponse>, AdminError> {
+    let config = entitlements_config(&state)?;
+    let services = get_services(&state)?;
+    let actor = AuditActor::from(&admin_auth);
+    let org = entitlements_org(services, &authz, &slug).await?;
+
+    entitlements
+        .validate(config)
+        .map_err(AdminError::Validation)?;
+
+    let updated = services
+        .organizations
+        .set_entitlements(org.id, Some(&entitlements))
+        .await?;
+    invalidate_entitlements(&state, &org).await;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "organization.entitlements_update".to_string(),
+            resource_type: "organization".to_string(),
+            resource_id: org.id,
+            org_id: Some(org.id),
+            project_id: None,
+            details: json!({
+                "slug": org.slug,
+                "previous": org.entitlements,
+                "entitlements": entitlements,
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(Json(
+        entitlements_response(services, config, &updated).await?,
+    ))
+}
+
+/// Remove an organization's entitlements
+///
+/// Puts the organization back on the default plan with no overrides.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    delete,
+    path = "/admin/v1/organizations/{slug}/entitlements",
+    tag = "organizations",
+    operation_id = "organization_delete_entitlements",
+    params(("slug" = String, Path, description = "Organization slug")),
+    responses(
+        (status = 200, description = "Entitlements removed"),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found or no entitlements set", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "Entitlements are not enabled", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn delete_entitlements(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path(slug): Path<String>,
+) -> Result<Json<()>, AdminError> {
+    entitlements_config(&state)?;
+    let services = get_services(&state)?;
+    let actor = AuditActor::from(&admin_auth);
+    let org = entitlements_org(services, &authz, &slug).await?;
+
+    if org.entitlements.is_none() {
+        return Err(AdminError::NotFound(format!(
+            "Organization '{}' has no entitlements set",
+            org.slug
+        )));
+    }
+
+    services
+        .organizations
+        .set_entitlements(org.id, None)
+        .await?;
+    invalidate_entitlements(&state, &org).await;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "organization.entitlements_delete".to_string(),
+            resource_type: "organization".to_string(),
+            resource_id: org.id,
+            org_id: Some(org.id),
+            project_id: None,
+            details: json!({
+                "slug": org.slug,
+                "previous": org.entitlements,
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(Json(()))
+}
+
+async fn invalidate_entitlements(state: &AppState, org: &Organization) {
+    if let Some(cache) = &state.cache {
+        let _ = cache.delete(&CacheKeys::org_entitlements(org.id)).await;
+    }
+}
+
+/// Query parameters for resolving allowed models.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema, utoipa::IntoParams))]
+pub struct AllowedModelsQuery {
+    /// Also apply this project's model access policy
+    pub project: Option<String>,
+}
+
+/// A model the caller may use.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct AllowedModel {
+    /// Model ID as sent in requests, e.g. `openai/gpt-4o`
+    pub id: String,
+    /// Provider the model is served by
+    pub provider: String,
+}
+
+/// Models allowed by an organization's (and optionally a project's) policy
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct AllowedModelsResponse {
+    /// Allowed models
+    pub data: Vec<AllowedModel>,
+}
+
+/// Resolve the models an organization's callers may use
+///
+/// Lists the models of the gateway's configured providers that pass the
+/// organization's model access policy and, when `project` is given, the
+/// project's. Intended for populating model pickers; API key model
+/// restrictions are not applied.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/organizations/{slug}/allowed-models",
+    tag = "organizations",
+    operation_id = "organization_allowed_models",
+    params(("slug" = String, Path, description = "Organization slug"), AllowedModelsQuery),
+    responses(
+        (status = 200, description = "Allowed models", body = AllowedModelsResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization or project not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn allowed_models(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(slug): Path<String>,
+    Query(query): Query<AllowedModelsQuery>,
+) -> Result<Json<AllowedModelsResponse>, AdminError> {
+    let services = get_services(&state)?;
+    let org = authorized_org(services, &authz, &slug, "read").await?;
+
+    let project_policy = match &query.project {
+        Some(project_slug) => {
+            services
+                .projects
+                .get_by_slug(org.id, project_slug)
+                .await?
+                .ok_or_else(|| {
+                    AdminError::NotFound(format!(
+                        "Project '{}' not found in organization '{}'",
+                        project_slug, org.slug
+                    ))
+                })?
+                .model_access
+        }
+        None => None,
+    };
+    let policies: Vec<&ModelAccessPolicy> = org
+        .model_access
+        .iter()
+        .chain(project_policy.iter())
+        .collect();
+
+    let data = crate::routes::api::models::static_provider_models(&state)
+        .await
+        .into_iter()
+        .flat_map(|(provider, models)| {
+            models.data.into_iter().map(move |model| AllowedModel {
+                id: format!("{}/{}", provider, model.id),
+                provider: provider.clone(),
+            })
+        })
+        .filter(|model| {
+            policies
+                .iter()
+                .all(|policy| policy.check(&model.provider, &model.id).is_ok())
+        })
+        .collect();
+
+    Ok(Json(AllowedModelsResponse { data }))
+}
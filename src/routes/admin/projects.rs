@@ -6,12 +6,21 @@ use axum::{
 use axum_valid::Valid;
 use serde::Serialize;
 use serde_json::json;
+use validator::Validate;
 
-use super::{AuditActor, error::AdminError, organizations::ListQuery};
+use super::{
+    AuditActor,
+    error::AdminError,
+    organizations::{LabelQuery, ListQuery},
+};
 use crate::{
     AppState,
+    cache::CacheKeys,
     middleware::{AdminAuth, AuthzContext, ClientInfo},
-    models::{CreateAuditLog, CreateProject, MembershipSource, Project, UpdateProject},
+    models::{
+        CreateAuditLog, CreateProject, MembershipSource, ModelAccessPolicy, Organization,
+        ParameterGovernance, Project, SetLabels, UpdateProject, validate_labels,
+    },
     openapi::PaginationMeta,
     services::Services,
 };
@@ -86,6 +95,12 @@ pub async fn create(
         }
     }
 
+    // Check the organization's plan
+    services
+        .entitlements
+        .check_project_limit(&state.config.features.entitlements, &org)
+        .await?;
+
     // Check project limit per team
     if let Some(team_id) = input.team_id {
         let max_per_team = limits.max_projects_per_team;
@@ -196,20 +211,22 @@ pub async fn get(
     params(
         ("org_slug" = String, Path, description = "Organization slug"),
         ListQuery,
+        LabelQuery,
     ),
     responses(
         (status = 200, description = "List of projects", body = ProjectListResponse),
-        (status = 400, description = "Invalid cursor or direction", body = crate::openapi::ErrorResponse),
+        (status = 400, description = "Invalid cursor, direction or label selector", body = crate::openapi::ErrorResponse),
         (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
         (status = 404, description = "Organization not found", body = crate::openapi::ErrorResponse),
     )
 ))]
-#[tracing::instrument(name = "admin.projects.list", skip(state, authz, query), fields(%org_slug))]
+#[tracing::instrument(name = "admin.projects.list", skip(state, authz, query, labels), fields(%org_slug))]
 pub async fn list(
     State(state): State<AppState>,
     Extension(authz): Extension<AuthzContext>,
     Path(org_slug): Path<String>,
     Query(query): Query<ListQuery>,
+    Query(labels): Query<LabelQuery>,
 ) -> Result<Json<ProjectListResponse>, AdminError> {
     let services = get_services(&state)?;
 
@@ -231,7 +248,7 @@ pub async fn list(
     )?;
 
     let limit = query.limit.unwrap_or(100);
-    let params = query.try_into_with_cursor()?;
+    let params = labels.apply(query.try_into_with_cursor()?)?;
 
     let result = services.projects.list_by_org(org.id, params).await?;
 
@@ -306,13 +323,28 @@ pub async fn update(
         Some(&project.id.to_string()),
     )?;
 
+    if let Some(Some(defaults)) = &input.request_defaults {
+        defaults
+            .validate()
+            .map_err(|e| AdminError::Validation(format!("request_defaults: {e}")))?;
+    }
+
     // Capture changes for audit log
     let changes = json!({
         "name": input.name,
+        "request_defaults": input.request_defaults,
     });
 
+    let defaults_changed = input.request_defaults.is_some();
+
     let updated = services.projects.update(project.id, input).await?;
 
+    if defaults_changed && let Some(cache) = &state.cache {
+        let _ = cache
+            .delete(&CacheKeys::project_request_defaults(project.id))
+            .await;
+    }
+
     // Log audit event (fire-and-forget)
     let _ = services
         .audit_logs
@@ -418,3 +450,442 @@ pub async fn delete(
 
     Ok(Json(()))
 }
+
+/// Look up a project by org and project slug and check `action` on it.
+async fn authorized_project(
+    services: &Services,
+    authz: &AuthzContext,
+    org_slug: &str,
+    project_slug: &str,
+    action: &str,
+) -> Result<(Organization, Project), AdminError> {
+    let org = services
+        .organizations
+        .get_by_slug(org_slug)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("Organization '{}' not found", org_slug)))?;
+
+    let project = services
+        .projects
+        .get_by_slug(org.id, project_slug)
+        .await?
+        .ok_or_else(|| {
+            AdminError::NotFound(format!(
+                "Project '{}' not found in organization '{}'",
+                project_slug, org_slug
+            ))
+        })?;
+
+    authz.require(
+        "project",
+        action,
+        Some(&project.id.to_string()),
+        Some(&org.id.to_string()),
+        project.team_id.as_ref().map(|t| t.to_string()).as_deref(),
+        Some(&project.id.to_string()),
+    )?;
+    Ok((org, project))
+}
+
+/// Set a project's labels
+///
+/// Replaces all of the project's labels. Send an empty object to remove
+/// them.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    put,
+    path = "/admin/v1/organizations/{org_slug}/projects/{project_slug}/labels",
+    tag = "projects",
+    operation_id = "project_set_labels",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("project_slug" = String, Path, description = "Project slug"),
+    ),
+    request_body = SetLabels,
+    responses(
+        (status = 200, description = "Labels saved", body = Project),
+        (status = 400, description = "Invalid labels", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization or project not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn set_labels(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path((org_slug, project_slug)): Path<(String, String)>,
+    Json(input): Json<SetLabels>,
+) -> Result<Json<Project>, AdminError> {
+    let services = get_services(&state)?;
+    let actor = AuditActor::from(&admin_auth);
+    let (org, project) =
+        authorized_project(services, &authz, &org_slug, &project_slug, "update").await?;
+
+    validate_labels(&input.labels).map_err(AdminError::Validation)?;
+
+    let updated = services
+        .projects
+        .set_labels(project.id, &input.labels)
+        .await?;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "project.labels_update".to_string(),
+            resource_type: "project".to_string(),
+            resource_id: project.id,
+            org_id: Some(org.id),
+            project_id: Some(project.id),
+            details: json!({
+                "slug": project.slug,
+                "previous": project.labels,
+                "labels": input.labels,
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(Json(updated))
+}
+
+/// Get a project's parameter governance rules
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/organizations/{org_slug}/projects/{project_slug}/parameter-governance",
+    tag = "projects",
+    operation_id = "project_get_parameter_governance",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("project_slug" = String, Path, description = "Project slug"),
+    ),
+    responses(
+        (status = 200, description = "Parameter governance rules", body = ParameterGovernance),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization or project not found, or no rules set", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn get_parameter_governance(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path((org_slug, project_slug)): Path<(String, String)>,
+) -> Result<Json<ParameterGovernance>, AdminError> {
+    let services = get_services(&state)?;
+    let (_, project) =
+        authorized_project(services, &authz, &org_slug, &project_slug, "read").await?;
+
+    project.parameter_governance.map(Json).ok_or_else(|| {
+        AdminError::NotFound(format!(
+            "Project '{}' has no parameter governance",
+            project.slug
+        ))
+    })
+}
+
+/// Set a project's parameter governance rules
+///
+/// Project rules run after the organization's, so they can tighten its
+/// bounds but not loosen them.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    put,
+    path = "/admin/v1/organizations/{org_slug}/projects/{project_slug}/parameter-governance",
+    tag = "projects",
+    operation_id = "project_set_parameter_governance",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("project_slug" = String, Path, description = "Project slug"),
+    ),
+    request_body = ParameterGovernance,
+    responses(
+        (status = 200, description = "Parameter governance saved", body = ParameterGovernance),
+        (status = 400, description = "Invalid rules", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization or project not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn set_parameter_governance(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path((org_slug, project_slug)): Path<(String, String)>,
+    Json(governance): Json<ParameterGovernance>,
+) -> Result<Json<ParameterGovernance>, AdminError> {
+    let services = get_services(&state)?;
+    let actor = AuditActor::from(&admin_auth);
+    let (org, project) =
+        authorized_project(services, &authz, &org_slug, &project_slug, "update").await?;
+
+    governance.validate().map_err(AdminError::Validation)?;
+
+    services
+        .projects
+        .set_parameter_governance(project.id, Some(&governance))
+        .await?;
+    invalidate_parameter_governance(&state, &project).await;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "project.parameter_governance_update".to_string(),
+            resource_type: "project".to_string(),
+            resource_id: project.id,
+            org_id: Some(org.id),
+            project_id: Some(project.id),
+            details: json!({
+                "slug": project.slug,
+                "previous": project.parameter_governance,
+                "rules": governance.rules,
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(Json(governance))
+}
+
+/// Remove a project's parameter governance rules
+///
+/// Organization rules still apply.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    delete,
+    path = "/admin/v1/organizations/{org_slug}/projects/{project_slug}/parameter-governance",
+    tag = "projects",
+    operation_id = "project_delete_parameter_governance",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("project_slug" = String, Path, description = "Project slug"),
+    ),
+    responses(
+        (status = 200, description = "Parameter governance removed"),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization or project not found, or no rules set", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn delete_parameter_governance(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path((org_slug, project_slug)): Path<(String, String)>,
+) -> Result<Json<()>, AdminError> {
+    let services = get_services(&state)?;
+    let actor = AuditActor::from(&admin_auth);
+    let (org, project) =
+        authorized_project(services, &authz, &org_slug, &project_slug, "update").await?;
+
+    if project.parameter_governance.is_none() {
+        return Err(AdminError::NotFound(format!(
+            "Project '{}' has no parameter governance",
+            project.slug
+        )));
+    }
+
+    services
+        .projects
+        .set_parameter_governance(project.id, None)
+        .await?;
+    invalidate_parameter_governance(&state, &project).await;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "project.parameter_governance_delete".to_string(),
+            resource_type: "project".to_string(),
+            resource_id: project.id,
+            org_id: Some(org.id),
+            project_id: Some(project.id),
+            details: json!({
+                "slug": project.slug,
+                "previous": project.parameter_governance,
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(Json(()))
+}
+
+async fn invalidate_parameter_governance(state: &AppState, project: &Project) {
+    if let Some(cache) = &state.cache {
+        let _ = cache
+            .delete(&CacheKeys::project_parameter_governance(project.id))
+            .await;
+    }
+}
+
+/// Get a project's model access policy
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/organizations/{org_slug}/projects/{project_slug}/model-access",
+    tag = "projects",
+    operation_id = "project_get_model_access",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("project_slug" = String, Path, description = "Project slug"),
+    ),
+    responses(
+        (status = 200, description = "Model access policy", body = ModelAccessPolicy),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization or project not found, or no policy set", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn get_model_access(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path((org_slug, project_slug)): Path<(String, String)>,
+) -> Result<Json<ModelAccessPolicy>, AdminError> {
+    let services = get_services(&state)?;
+    let (_, project) =
+        authorized_project(services, &authz, &org_slug, &project_slug, "read").await?;
+
+    project.model_access.map(Json).ok_or_else(|| {
+        AdminError::NotFound(format!(
+            "Project '{}' has no model access policy",
+            project.slug
+        ))
+    })
+}
+
+/// Set a project's model access policy
+///
+/// Checked in addition to the organization's policy: a request must pass
+/// both.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    put,
+    path = "/admin/v1/organizations/{org_slug}/projects/{project_slug}/model-access",
+    tag = "projects",
+    operation_id = "project_set_model_access",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("project_slug" = String, Path, description = "Project slug"),
+    ),
+    request_body = ModelAccessPolicy,
+    responses(
+        (status = 200, description = "Model access policy saved", body = ModelAccessPolicy),
+        (status = 400, description = "Invalid policy", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization or project not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn set_model_access(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path((org_slug, project_slug)): Path<(String, String)>,
+    Json(policy): Json<ModelAccessPolicy>,
+) -> Result<Json<ModelAccessPolicy>, AdminError> {
+    let services = get_services(&state)?;
+    let actor = AuditActor::from(&admin_auth);
+    let (org, project) =
+        authorized_project(services, &authz, &org_slug, &project_slug, "update").await?;
+
+    policy.validate().map_err(AdminError::Validation)?;
+
+    services
+        .projects
+        .set_model_access(project.id, Some(&policy))
+        .await?;
+    invalidate_model_access(&state, &project).await;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "project.model_access_update".to_string(),
+            resource_type: "project".to_string(),
+            resource_id: project.id,
+            org_id: Some(org.id),
+            project_id: Some(project.id),
+            details: json!({
+                "slug": project.slug,
+                "previous": project.model_access,
+                "policy": policy,
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(Json(policy))
+}
+
+/// Remove a project's model access policy
+///
+/// The organization's policy still applies.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    delete,
+    path = "/admin/v1/organizations/{org_slug}/projects/{project_slug}/model-access",
+    tag = "projects",
+    operation_id = "project_delete_model_access",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("project_slug" = String, Path, description = "Project slug"),
+    ),
+    responses(
+        (status = 200, description = "Model access policy removed"),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization or project not found, or no policy set", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn delete_model_access(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path((org_slug, project_slug)): Path<(String, String)>,
+) -> Result<Json<()>, AdminError> {
+    let services = get_services(&state)?;
+    let actor = AuditActor::from(&admin_auth);
+    let (org, project) =
+        authorized_project(services, &authz, &org_slug, &project_slug, "update").await?;
+
+    if project.model_access.is_none() {
+        return Err(AdminError::NotFound(format!(
+            "Project '{}' has no model access policy",
+            project.slug
+        )));
+    }
+
+    services.projects.set_model_access(project.id, None).await?;
+    invalidate_model_access(&state, &project).await;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "project.model_access_delete".to_string(),
+            resource_type: "project".to_string(),
+            resource_id: project.id,
+            org_id: Some(org.id),
+            project_id: Some(project.id),
+            details: json!({
+                "slug": project.slug,
+                "previous": project.model_access,
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(Json(()))
+}
+
+async fn invalidate_model_access(state: &AppState, project: &Project) {
+    if let Some(cache) = &state.cache {
+        let _ = cache
+            .delete(&CacheKeys::project_model_access(project.id))
+            .await;
+    }
+}
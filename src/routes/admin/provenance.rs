@@ -0,0 +1,105 @@
+//! Admin endpoint for verifying provenance metadata issued with completion
+//! responses. See [`crate::services::provenance`].
+
+use axum::{Extension, Json, extract::State};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::AdminError;
+use crate::{AppState, middleware::AuthzContext, services::provenance::ProvenanceMetadata};
+
+/// Provenance metadata as returned with a response.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(untagged)]
+pub enum ProvenanceMetadataInput {
+    /// The `provenance` field of a response body
+    Metadata(ProvenanceMetadata),
+    /// The `X-Hadrian-Provenance` header value
+    Header(String),
+}
+
+/// A text and the provenance metadata it was returned with.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct VerifyProvenanceRequest {
+    /// The generated text, exactly as returned
+    pub text: String,
+    /// Metadata returned with the text
+    pub metadata: ProvenanceMetadataInput,
+}
+
+/// Result of a provenance verification.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct VerifyProvenanceResponse {
+    /// Whether the text was generated through this gateway with this metadata
+    pub valid: bool,
+    /// Why verification failed: `unsupported_version`, `invalid_signature`
+    /// or `content_mismatch`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<&'static str>,
+    /// Request that generated the text, when valid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    /// Model that generated the text, when valid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// When the metadata was signed, when valid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// Verify provenance metadata
+///
+/// Checks that the metadata was signed by this gateway and that the text is
+/// the one it was issued for. A text that was edited, even by whitespace,
+/// fails with `content_mismatch`.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/provenance/verify",
+    tag = "provenance",
+    operation_id = "provenance_verify",
+    request_body = VerifyProvenanceRequest,
+    responses(
+        (status = 200, description = "Verification result", body = VerifyProvenanceResponse),
+        (status = 400, description = "Malformed metadata", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "Provenance is not enabled", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.provenance.verify", skip(state, authz, request))]
+pub async fn verify(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Json(request): Json<VerifyProvenanceRequest>,
+) -> Result<Json<VerifyProvenanceResponse>, AdminError> {
+    authz.require("provenance", "verify", None, None, None, None)?;
+    let key = state.config.features.provenance.key().ok_or_else(|| {
+        AdminError::NotConfigured("Provenance is not enabled ([features.provenance])".to_string())
+    })?;
+
+    let metadata = match request.metadata {
+        ProvenanceMetadataInput::Metadata(metadata) => metadata,
+        ProvenanceMetadataInput::Header(value) => ProvenanceMetadata::from_header(&value)
+            .ok_or_else(|| {
+                AdminError::BadRequest("metadata is not a valid provenance header".to_string())
+            })?,
+    };
+
+    Ok(Json(match metadata.verify(key, &request.text) {
+        Ok(()) => VerifyProvenanceResponse {
+            valid: true,
+            reason: None,
+            created_at: DateTime::from_timestamp(metadata.created_at, 0),
+            request_id: Some(metadata.request_id),
+            model: Some(metadata.model),
+        },
+        Err(mismatch) => VerifyProvenanceResponse {
+            valid: false,
+            reason: Some(mismatch.as_str()),
+            request_id: None,
+            model: None,
+            created_at: None,
+        },
+    }))
+}
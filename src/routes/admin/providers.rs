@@ -1,21 +1,29 @@
 //! Provider administration endpoints.
 //!
 //! Provides endpoints for monitoring provider health, circuit breaker status,
-//! and metrics-based statistics.
+//! and metrics-based statistics, and for changing the runtime settings of
+//! providers from the config file.
 
 use axum::{
     Extension, Json,
     extract::{Path, Query, State},
 };
+use axum_valid::Valid;
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 
-use super::AdminError;
+use super::{AdminError, AuditActor};
 use crate::{
     AppState,
+    config::ProviderConfig,
     jobs::ProviderHealthState,
-    middleware::AuthzContext,
-    providers::CircuitBreakerStatus,
+    middleware::{AdminAuth, AuthzContext, ClientInfo},
+    models::{
+        CircuitBreakerSettings, CreateAuditLog, ProviderSettings, RetrySettings,
+        UpdateProviderSettings,
+    },
+    providers::{CircuitBreakerStatus, adaptive_concurrency::ConcurrencyStatus},
     services::{ProviderStats, ProviderStatsHistorical, StatsGranularity},
 };
 
@@ -102,6 +110,42 @@ pub async fn get_circuit_breaker(
     }))
 }
 
+/// Get the adaptive concurrency limit for a specific provider.
+///
+/// Returns the current limit, requests in flight and waiting, and the
+/// latencies the limit is being adjusted against.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/providers/{provider_name}/concurrency",
+    tag = "providers",
+    params(
+        ("provider_name" = String, Path, description = "Provider name")
+    ),
+    responses(
+        (status = 200, description = "Adaptive concurrency status for the provider", body = ConcurrencyStatus),
+        (status = 404, description = "Provider not found or adaptive concurrency not enabled"),
+    )
+))]
+pub async fn get_concurrency(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    axum::extract::Path(provider_name): axum::extract::Path<String>,
+) -> Result<Json<ConcurrencyStatus>, AdminError> {
+    authz.require("provider", "read", None, None, None, None)?;
+
+    let status = state
+        .adaptive_limiters
+        .status_for(&provider_name)
+        .ok_or_else(|| {
+            AdminError::NotFound(format!(
+                "Adaptive concurrency is not enabled for provider '{}'",
+                provider_name
+            ))
+        })?;
+
+    Ok(Json(status))
+}
+
 /// Response for provider health status endpoint.
 #[derive(Debug, Serialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
@@ -271,8 +315,11 @@ pub async fn get_provider_stats(
 /// on the granularity parameter.
 ///
 /// **Note:** Historical stats require Prometheus to be configured via
-/// `observability.metrics.prometheus_query_url`. In single-node deployments
-/// without Prometheus, this endpoint returns an error.
+/// `observability.metrics.prometheus_query_url`. Without Prometheus, `data`
+/// is empty and `prometheus_configured` is false.
+///
+/// Results of scheduled probes (`health_check.probe`) are returned in
+/// `probes` from the database, with or without Prometheus.
 #[cfg_attr(feature = "utoipa", utoipa::path(
     get,
     path = "/admin/v1/providers/{provider_name}/stats/history",
@@ -333,6 +380,15 @@ pub async fn get_provider_stats_history(
         )));
     }
 
+    let probes = match &state.db {
+        Some(db) => {
+            db.provider_probes()
+                .history(&provider_name, start, end, granularity.duration_secs())
+                .await?
+        }
+        None => vec![],
+    };
+
     // Return empty data if Prometheus is not configured (let frontend handle display)
     if !state.provider_metrics.has_prometheus() {
         return Ok(Json(ProviderStatsHistorical {
@@ -340,14 +396,232 @@ pub async fn get_provider_stats_history(
             granularity,
             data: vec![],
             prometheus_configured: false,
+            probes,
         }));
     }
 
-    let historical = state
+    let mut historical = state
         .provider_metrics
         .get_historical(&provider_name, start, end, granularity)
         .await
         .map_err(|e| AdminError::Internal(format!("Failed to get historical stats: {}", e)))?;
+    historical.probes = probes;
 
     Ok(Json(historical))
 }
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Provider Settings Endpoints
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Runtime settings of a static provider, with the values in effect.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ProviderSettingsResponse {
+    /// Provider name.
+    pub provider: String,
+    /// Settings stored in the database, if any.
+    pub settings: Option<ProviderSettings>,
+    /// Whether requests are routed to the provider.
+    pub enabled: bool,
+    /// Circuit breaker parameters in effect.
+    pub circuit_breaker: CircuitBreakerSettings,
+    /// Retry parameters in effect.
+    pub retry: RetrySettings,
+    /// Fair queue concurrency limit in effect, if the provider has a fair queue.
+    pub max_concurrent: Option<u32>,
+}
+
+/// Look up a provider in the config file. Only static providers have
+/// runtime settings.
+fn static_provider<'a>(state: &'a AppState, name: &str) -> Result<&'a ProviderConfig, AdminError> {
+    state
+        .config
+        .providers
+        .get(name)
+        .ok_or_else(|| AdminError::NotFound(format!("Provider '{}' not found", name)))
+}
+
+fn settings_response(
+    name: &str,
+    file_config: &ProviderConfig,
+    settings: Option<ProviderSettings>,
+) -> ProviderSettingsResponse {
+    let mut config = file_config.clone();
+    if let Some(settings) = &settings {
+        settings.apply_to(&mut config);
+    }
+    ProviderSettingsResponse {
+        provider: name.to_string(),
+        enabled: settings.as_ref().is_none_or(ProviderSettings::is_enabled),
+        circuit_breaker: config.circuit_breaker_config().into(),
+        retry: config.retry_config().into(),
+        max_concurrent: config.fair_queue_config().map(|fq| fq.max_concurrent),
+        settings,
+    }
+}
+
+/// Get the runtime settings of a provider.
+///
+/// Returns the settings stored for a provider from the config file, along
+/// with the values in effect once they're applied on top of the file.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/providers/{provider_name}/settings",
+    tag = "providers",
+    params(
+        ("provider_name" = String, Path, description = "Provider name")
+    ),
+    responses(
+        (status = 200, description = "Settings for the provider", body = ProviderSettingsResponse),
+        (status = 404, description = "Provider not found in the config file"),
+    )
+))]
+pub async fn get_provider_settings(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(provider_name): Path<String>,
+) -> Result<Json<ProviderSettingsResponse>, AdminError> {
+    authz.require("provider", "read", Some(&provider_name), None, None, None)?;
+
+    let file_config = static_provider(&state, &provider_name)?;
+    let db = state.db.as_ref().ok_or(AdminError::DatabaseRequired)?;
+    let settings = db.provider_settings().get(&provider_name).await?;
+
+    Ok(Json(settings_response(
+        &provider_name,
+        file_config,
+        settings,
+    )))
+}
+
+/// Set the runtime settings of a provider.
+///
+/// Enables or disables a provider from the config file, or tunes its
+/// circuit breaker, retries and fair queue concurrency. Replaces any settings
+/// set before; omitted fields fall back to the config file. Takes effect on
+/// this node immediately and on other nodes within 30 seconds. Changing
+/// circuit breaker settings resets the provider's breaker.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    put,
+    path = "/admin/v1/providers/{provider_name}/settings",
+    tag = "providers",
+    params(
+        ("provider_name" = String, Path, description = "Provider name")
+    ),
+    request_body = UpdateProviderSettings,
+    responses(
+        (status = 200, description = "Settings updated", body = ProviderSettingsResponse),
+        (status = 400, description = "Invalid settings"),
+        (status = 404, description = "Provider not found in the config file"),
+    )
+))]
+pub async fn update_provider_settings(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path(provider_name): Path<String>,
+    Valid(Json(input)): Valid<Json<UpdateProviderSettings>>,
+) -> Result<Json<ProviderSettingsResponse>, AdminError> {
+    authz.require("provider", "update", Some(&provider_name), None, None, None)?;
+
+    let file_config = static_provider(&state, &provider_name)?;
+    input
+        .validate_for(file_config)
+        .map_err(AdminError::Validation)?;
+    let db = state.db.as_ref().ok_or(AdminError::DatabaseRequired)?;
+    let actor = AuditActor::from(&admin_auth);
+
+    let previous = db.provider_settings().get(&provider_name).await?;
+    let settings = db
+        .provider_settings()
+        .upsert(&provider_name, input, actor.actor_id)
+        .await?;
+    state.provider_settings.set(settings.clone());
+
+    if let Some(services) = &state.services {
+        let _ = services
+            .audit_logs
+            .create(CreateAuditLog {
+                actor_type: actor.actor_type,
+                actor_id: actor.actor_id,
+                action: "provider_settings.update".to_string(),
+                resource_type: "provider_settings".to_string(),
+                resource_id: settings.id,
+                org_id: None,
+                project_id: None,
+                details: json!({
+                    "provider": provider_name,
+                    "previous": previous,
+                    "settings": settings,
+                }),
+                ip_address: client_info.ip_address,
+                user_agent: client_info.user_agent,
+            })
+            .await;
+    }
+
+    Ok(Json(settings_response(
+        &provider_name,
+        file_config,
+        Some(settings),
+    )))
+}
+
+/// Reset the runtime settings of a provider.
+///
+/// Deletes the stored settings so the provider goes back to its config file
+/// values.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    delete,
+    path = "/admin/v1/providers/{provider_name}/settings",
+    tag = "providers",
+    params(
+        ("provider_name" = String, Path, description = "Provider name")
+    ),
+    responses(
+        (status = 200, description = "Settings reset", body = ProviderSettingsResponse),
+        (status = 404, description = "Provider not found in the config file"),
+    )
+))]
+pub async fn delete_provider_settings(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path(provider_name): Path<String>,
+) -> Result<Json<ProviderSettingsResponse>, AdminError> {
+    authz.require("provider", "update", Some(&provider_name), None, None, None)?;
+
+    let file_config = static_provider(&state, &provider_name)?;
+    let db = state.db.as_ref().ok_or(AdminError::DatabaseRequired)?;
+
+    let previous = db.provider_settings().get(&provider_name).await?;
+    db.provider_settings().delete(&provider_name).await?;
+    state.provider_settings.remove(&provider_name);
+
+    if let (Some(previous), Some(services)) = (previous, &state.services) {
+        let actor = AuditActor::from(&admin_auth);
+        let _ = services
+            .audit_logs
+            .create(CreateAuditLog {
+                actor_type: actor.actor_type,
+                actor_id: actor.actor_id,
+                action: "provider_settings.delete".to_string(),
+                resource_type: "provider_settings".to_string(),
+                resource_id: previous.id,
+                org_id: None,
+                project_id: None,
+                details: json!({
+                    "provider": provider_name,
+                    "previous": previous,
+                }),
+                ip_address: client_info.ip_address,
+                user_agent: client_info.user_agent,
+            })
+            .await;
+    }
+
+    Ok(Json(settings_response(&provider_name, file_config, None)))
+}
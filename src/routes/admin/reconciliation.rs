@@ -0,0 +1,310 @@
+//! Admin API endpoints for provider usage imports and the variance report
+//! comparing imported usage with what the gateway recorded.
+
+use axum::{
+    Extension, Json,
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use chrono::NaiveDate;
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use super::{AdminError, AuditActor, usage::UsageReconciliationResponse};
+use crate::{
+    AppState,
+    db::DateRange,
+    middleware::{AdminAuth, AuthzContext, ClientInfo},
+    models::{CreateAuditLog, ProviderUsageImport, UsageImportSource},
+    services::Services,
+};
+
+/// Query parameters for the variance report
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema, utoipa::IntoParams))]
+pub struct ReconciliationQuery {
+    /// Start date (YYYY-MM-DD). Defaults to 30 days before `end_date`.
+    pub start_date: Option<NaiveDate>,
+    /// End date (YYYY-MM-DD), inclusive. Defaults to today (UTC).
+    pub end_date: Option<NaiveDate>,
+    /// Only include this provider
+    pub provider: Option<String>,
+}
+
+/// Query parameters for listing imports
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema, utoipa::IntoParams))]
+pub struct ListUsageImportsQuery {
+    /// Only include imports for this provider
+    pub provider: Option<String>,
+}
+
+/// Query parameters for a CSV upload
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema, utoipa::IntoParams))]
+pub struct CsvUsageImportQuery {
+    /// Provider name in `[providers]` the export belongs to
+    pub provider: String,
+    /// Which provider console or billing export the file came from
+    pub source: UsageImportSource,
+}
+
+/// Pull usage from a provider's usage API
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ApiUsageImportRequest {
+    /// Provider name in `[features.usage_reconciliation].sources`
+    pub provider: String,
+    /// First day to import (YYYY-MM-DD)
+    pub start_date: NaiveDate,
+    /// Last day to import (YYYY-MM-DD), inclusive
+    pub end_date: NaiveDate,
+}
+
+fn get_services(state: &AppState) -> Result<&Services, AdminError> {
+    state.services.as_ref().ok_or(AdminError::ServicesRequired)
+}
+
+async fn audit_import(
+    services: &Services,
+    admin_auth: &AdminAuth,
+    client_info: ClientInfo,
+    import: &ProviderUsageImport,
+) {
+    let actor = AuditActor::from(admin_auth);
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "provider_usage.import".to_string(),
+            resource_type: "provider_usage_import".to_string(),
+            resource_id: import.id,
+            org_id: None,
+            project_id: None,
+            details: json!({
+                "provider": import.provider,
+                "source": import.source,
+                "method": import.method,
+                "start_date": import.start_date,
+                "end_date": import.end_date,
+                "row_count": import.row_count,
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+}
+
+/// Get the variance report
+///
+/// Compares imported provider usage with gateway usage per provider, day and
+/// model. Only days covered by an import have provider counts; other days
+/// show gateway counts alone.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/reconciliation",
+    tag = "reconciliation",
+    operation_id = "reconciliation_report",
+    params(ReconciliationQuery),
+    responses(
+        (status = 200, description = "Variance report", body = UsageReconciliationResponse),
+        (status = 400, description = "Invalid date range", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn report(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Query(query): Query<ReconciliationQuery>,
+) -> Result<Json<UsageReconciliationResponse>, AdminError> {
+    authz.require("reconciliation", "read", None, None, None, None)?;
+    let services = get_services(&state)?;
+
+    let end = query
+        .end_date
+        .unwrap_or_else(|| chrono::Utc::now().date_naive());
+    let start = query.start_date.unwrap_or(end - chrono::Duration::days(30));
+    if end < start {
+        return Err(AdminError::BadRequest(
+            "end_date must be >= start_date".to_string(),
+        ));
+    }
+
+    let providers = services
+        .provider_usage_imports
+        .report(DateRange { start, end }, query.provider.as_deref())
+        .await?;
+    Ok(Json(UsageReconciliationResponse {
+        start_date: start,
+        end_date: end,
+        providers,
+    }))
+}
+
+/// List usage imports
+///
+/// Returns imports newest first.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/reconciliation/imports",
+    tag = "reconciliation",
+    operation_id = "reconciliation_import_list",
+    params(ListUsageImportsQuery),
+    responses(
+        (status = 200, description = "Usage imports", body = Vec<ProviderUsageImport>),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn list_imports(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Query(query): Query<ListUsageImportsQuery>,
+) -> Result<Json<Vec<ProviderUsageImport>>, AdminError> {
+    authz.require("reconciliation", "list", None, None, None, None)?;
+    let services = get_services(&state)?;
+    let imports = services
+        .provider_usage_imports
+        .list(query.provider.as_deref())
+        .await?;
+    Ok(Json(imports))
+}
+
+/// Import usage from a provider API
+///
+/// Pulls daily usage from the provider's usage API configured in
+/// `[features.usage_reconciliation].sources`, replacing earlier imported
+/// usage for the same days.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/reconciliation/imports",
+    tag = "reconciliation",
+    operation_id = "reconciliation_import_api",
+    request_body = ApiUsageImportRequest,
+    responses(
+        (status = 201, description = "Usage imported", body = ProviderUsageImport),
+        (status = 400, description = "Invalid range or provider API error", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "No usage API configured for the provider", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn import_from_api(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Json(input): Json<ApiUsageImportRequest>,
+) -> Result<(StatusCode, Json<ProviderUsageImport>), AdminError> {
+    authz.require("reconciliation", "create", None, None, None, None)?;
+    let services = get_services(&state)?;
+    if input.end_date < input.start_date {
+        return Err(AdminError::BadRequest(
+            "end_date must be >= start_date".to_string(),
+        ));
+    }
+
+    let range = DateRange {
+        start: input.start_date,
+        end: input.end_date,
+    };
+    let import = services
+        .provider_usage_imports
+        .import_from_api(
+            &state.http_client,
+            &state.config.features.usage_reconciliation,
+            &input.provider,
+            range,
+        )
+        .await?;
+
+    audit_import(services, &admin_auth, client_info, &import).await;
+    Ok((StatusCode::CREATED, Json(import)))
+}
+
+/// Import usage from a CSV export
+///
+/// Accepts a usage or cost export from the OpenAI or Anthropic console, or
+/// an Azure Cost Management export. The import covers the first through the
+/// last day in the file and replaces earlier imported usage for those days.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/reconciliation/imports/csv",
+    tag = "reconciliation",
+    operation_id = "reconciliation_import_csv",
+    params(CsvUsageImportQuery),
+    request_body(content = String, content_type = "text/csv"),
+    responses(
+        (status = 201, description = "Usage imported", body = ProviderUsageImport),
+        (status = 400, description = "Invalid CSV", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn import_csv(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Query(query): Query<CsvUsageImportQuery>,
+    body: Bytes,
+) -> Result<(StatusCode, Json<ProviderUsageImport>), AdminError> {
+    authz.require("reconciliation", "create", None, None, None, None)?;
+    let services = get_services(&state)?;
+    let body = std::str::from_utf8(&body)
+        .map_err(|_| AdminError::BadRequest("CSV body must be UTF-8".to_string()))?;
+
+    let import = services
+        .provider_usage_imports
+        .import_csv(&query.provider, query.source, body)
+        .await?;
+
+    audit_import(services, &admin_auth, client_info, &import).await;
+    Ok((StatusCode::CREATED, Json(import)))
+}
+
+/// Delete a usage import
+///
+/// Removes the import and the usage it still provides. Days it covered have
+/// no provider counts until they are imported again.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    delete,
+    path = "/admin/v1/reconciliation/imports/{id}",
+    tag = "reconciliation",
+    operation_id = "reconciliation_import_delete",
+    params(("id" = Uuid, Path, description = "Import ID")),
+    responses(
+        (status = 204, description = "Import deleted"),
+        (status = 404, description = "Import not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn delete_import(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AdminError> {
+    authz.require("reconciliation", "delete", None, None, None, None)?;
+    let services = get_services(&state)?;
+    if !services.provider_usage_imports.delete(id).await? {
+        return Err(AdminError::NotFound(format!(
+            "Usage import '{id}' not found"
+        )));
+    }
+
+    let actor = AuditActor::from(&admin_auth);
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "provider_usage.delete".to_string(),
+            resource_type: "provider_usage_import".to_string(),
+            resource_id: id,
+            org_id: None,
+            project_id: None,
+            details: json!({}),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+    Ok(StatusCode::NO_CONTENT)
+}
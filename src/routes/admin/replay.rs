@@ -0,0 +1,169 @@
+//! Admin endpoint for replaying stored Responses API requests.
+//!
+//! `POST /admin/v1/organizations/{org_slug}/responses/{response_id}/replay`
+//! re-sends a persisted request, optionally to another model or provider,
+//! and diffs the new output against the stored one. See
+//! [`crate::services::replay`] for what a replay does and doesn't do.
+
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+};
+use serde::Deserialize;
+use serde_json::json;
+
+use super::{AuditActor, error::AdminError};
+use crate::{
+    AppState,
+    middleware::{AdminAuth, AuthzContext, ClientInfo},
+    models::CreateAuditLog,
+    services::{
+        ResponsesStoreError,
+        replay::{ReplayError, ReplayMode, ReplayOptions, ReplayOutcome, replay_response},
+    },
+};
+
+/// Request to replay a stored response.
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ReplayRequest {
+    /// Model to replay against (defaults to the original model)
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Static provider to replay against (defaults to the original provider)
+    #[serde(default)]
+    pub provider: Option<String>,
+    /// `dry_run` (default) resolves the target without calling it; `live`
+    /// calls the provider and diffs the outputs
+    #[serde(default)]
+    pub mode: ReplayMode,
+}
+
+impl From<ReplayError> for AdminError {
+    fn from(err: ReplayError) -> Self {
+        match err {
+            ReplayError::NotReplayable(_) | ReplayError::Routing(_) => {
+                AdminError::BadRequest(err.to_string())
+            }
+            ReplayError::Execution(_) => AdminError::Internal(err.to_string()),
+        }
+    }
+}
+
+/// Replay a stored response
+///
+/// Only responses persisted by `POST /v1/responses` can be replayed; usage
+/// logs don't retain request bodies. Live replays make one upstream call
+/// (no fallbacks, no tool execution) that is billed by the provider but not
+/// recorded as gateway usage.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/organizations/{org_slug}/responses/{response_id}/replay",
+    tag = "usage",
+    operation_id = "response_replay",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("response_id" = String, Path, description = "Stored response ID (`resp_...`)"),
+    ),
+    request_body = ReplayRequest,
+    responses(
+        (status = 200, description = "Replay result", body = ReplayOutcome),
+        (status = 400, description = "Payload cannot be replayed or target cannot be routed", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization or response not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.responses.replay", skip(state, admin_auth, authz, request), fields(%org_slug, %response_id))]
+pub async fn replay(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path((org_slug, response_id)): Path<(String, String)>,
+    Json(request): Json<ReplayRequest>,
+) -> Result<Json<ReplayOutcome>, AdminError> {
+    let services = state
+        .services
+        .as_ref()
+        .ok_or(AdminError::ServicesRequired)?;
+    let store = state
+        .responses_store
+        .as_deref()
+        .ok_or(AdminError::DatabaseRequired)?;
+    let actor = AuditActor::from(&admin_auth);
+
+    let org = services
+        .organizations
+        .get_by_slug(&org_slug)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("Organization '{}' not found", org_slug)))?;
+
+    let record = match store.get(&response_id, org.id).await {
+        Ok(record) => record,
+        Err(ResponsesStoreError::NotFound) => {
+            return Err(AdminError::NotFound(format!(
+                "Response '{}' not found",
+                response_id
+            )));
+        }
+        Err(ResponsesStoreError::Database(e)) => return Err(e.into()),
+        Err(e) => return Err(AdminError::Internal(e.to_string())),
+    };
+
+    let org_id_str = org.id.to_string();
+    let project_id_str = record.project_id.map(|id| id.to_string());
+    authz.require(
+        "response",
+        "replay",
+        Some(&response_id),
+        Some(&org_id_str),
+        None,
+        project_id_str.as_deref(),
+    )?;
+
+    let mode = request.mode;
+    let outcome = replay_response(
+        &state,
+        store,
+        &record,
+        ReplayOptions {
+            model: request.model,
+            provider: request.provider,
+            mode,
+        },
+    )
+    .await?;
+
+    // Live replays spend provider credit, so they're audited; dry runs only
+    // read data the caller could already see
+    if mode == ReplayMode::Live {
+        let replay = outcome.replay.as_ref();
+        let _ = services
+            .audit_logs
+            .create(CreateAuditLog {
+                actor_type: actor.actor_type,
+                actor_id: actor.actor_id,
+                action: "response.replay".to_string(),
+                resource_type: "organization".to_string(),
+                resource_id: org.id,
+                org_id: Some(org.id),
+                project_id: record.project_id,
+                details: json!({
+                    "response_id": response_id,
+                    "original_provider": record.provider,
+                    "original_model": record.model,
+                    "provider": outcome.target.provider,
+                    "model": outcome.target.model,
+                    "status_code": replay.and_then(|r| r.status_code),
+                    "input_tokens": replay.and_then(|r| r.input_tokens),
+                    "output_tokens": replay.and_then(|r| r.output_tokens),
+                    "identical": outcome.diff.as_ref().map(|d| d.identical),
+                }),
+                ip_address: client_info.ip_address,
+                user_agent: client_info.user_agent,
+            })
+            .await;
+    }
+
+    Ok(Json(outcome))
+}
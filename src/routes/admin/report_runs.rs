@@ -0,0 +1,188 @@
+//! Admin API endpoints for scheduled report delivery history.
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::error::AdminError;
+use crate::{
+    AppState,
+    db::{Cursor, CursorDirection, ListParams},
+    middleware::AuthzContext,
+    models::{ReportRun, ReportRunFilter},
+    openapi::PaginationMeta,
+    services::ReportRunService,
+};
+
+/// Query parameters for listing report runs.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema, utoipa::IntoParams))]
+pub struct ReportRunListQuery {
+    /// Filter by organization ID.
+    pub org_id: Option<Uuid>,
+    /// Filter by report name from `[features.scheduled_reports]`.
+    pub report_name: Option<String>,
+    /// Maximum number of runs to return (default: 100).
+    pub limit: Option<i64>,
+    /// Cursor for keyset pagination. Encoded as base64 string.
+    #[cfg_attr(
+        feature = "utoipa",
+        schema(example = "MTczMzU4MDgwMDAwMDphYmMxMjM0NS02Nzg5LTAxMjMtNDU2Ny0wMTIzNDU2Nzg5YWI")
+    )]
+    pub cursor: Option<String>,
+    /// Pagination direction: "forward" (default) or "backward".
+    #[serde(default)]
+    pub direction: Option<String>,
+}
+
+impl ReportRunListQuery {
+    /// Split into repository filter and list params, rejecting invalid cursors.
+    fn try_into_parts(self) -> Result<(ReportRunFilter, ListParams), AdminError> {
+        let cursor = match &self.cursor {
+            Some(c) => Some(
+                Cursor::decode(c)
+                    .map_err(|e| AdminError::BadRequest(format!("Invalid cursor: {}", e)))?,
+            ),
+            None => None,
+        };
+
+        let direction = match self.direction.as_deref() {
+            Some("backward") => CursorDirection::Backward,
+            Some("forward") | None => CursorDirection::Forward,
+            Some(other) => {
+                return Err(AdminError::BadRequest(format!(
+                    "Invalid direction '{}': must be 'forward' or 'backward'",
+                    other
+                )));
+            }
+        };
+
+        let filter = ReportRunFilter {
+            org_id: self.org_id,
+            report_name: self.report_name,
+        };
+        let params = ListParams {
+            limit: Some(self.limit.unwrap_or(100)),
+            cursor,
+            direction,
+            ..Default::default()
+        }
+        .clamp();
+        Ok((filter, params))
+    }
+}
+
+/// Paginated list of report runs
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ReportRunListResponse {
+    /// List of report runs (without rendered content)
+    pub data: Vec<ReportRun>,
+    /// Pagination metadata
+    pub pagination: PaginationMeta,
+}
+
+fn get_service(state: &AppState) -> Result<&ReportRunService, AdminError> {
+    state
+        .services
+        .as_ref()
+        .map(|s| &s.report_runs)
+        .ok_or(AdminError::ServicesRequired)
+}
+
+/// List scheduled report runs
+///
+/// Returns delivery attempts newest first. Rendered report bodies are omitted;
+/// fetch a single run to retrieve its content.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/report-runs",
+    tag = "reports",
+    operation_id = "report_run_list",
+    params(ReportRunListQuery),
+    responses(
+        (status = 200, description = "List of report runs", body = ReportRunListResponse),
+        (status = 400, description = "Invalid cursor or direction", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Organization outside caller's scope", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn list(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Query(query): Query<ReportRunListQuery>,
+) -> Result<Json<ReportRunListResponse>, AdminError> {
+    let service = get_service(&state)?;
+    let (mut filter, params) = query.try_into_parts()?;
+    let limit = params.limit.unwrap_or(100);
+
+    // Reports carry usage and key inventory for a single tenant, so org
+    // members only ever see their own organization's runs.
+    if let Some(membership) = authz.subject.org_ids.first() {
+        let scoped: Uuid = membership.parse().map_err(|_| {
+            AdminError::Internal(
+                "report:list authz subject has a non-UUID org membership".to_string(),
+            )
+        })?;
+        match filter.org_id {
+            Some(requested) if requested != scoped => {
+                return Err(AdminError::Forbidden(
+                    "report:list scoped outside your organization".to_string(),
+                ));
+            }
+            _ => filter.org_id = Some(scoped),
+        }
+    }
+
+    let org_scope = filter.org_id.map(|id| id.to_string());
+    authz.require("report", "list", None, org_scope.as_deref(), None, None)?;
+
+    let result = service.list(filter, params).await?;
+
+    let pagination = PaginationMeta::with_cursors(
+        limit,
+        result.has_more,
+        result.cursors.next.map(|c| c.encode()),
+        result.cursors.prev.map(|c| c.encode()),
+    );
+
+    Ok(Json(ReportRunListResponse {
+        data: result.items,
+        pagination,
+    }))
+}
+
+/// Get a scheduled report run by ID
+///
+/// Includes the rendered report body as it was (or would have been) delivered.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/report-runs/{id}",
+    tag = "reports",
+    operation_id = "report_run_get",
+    params(("id" = Uuid, Path, description = "Report run ID")),
+    responses(
+        (status = 200, description = "Report run found", body = ReportRun),
+        (status = 404, description = "Report run not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn get(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ReportRun>, AdminError> {
+    let service = get_service(&state)?;
+
+    let run = service
+        .get_by_id(id)
+        .await?
+        .ok_or_else(|| AdminError::NotFound("Report run not found".to_string()))?;
+
+    let id_str = id.to_string();
+    let org_id = run.org_id.to_string();
+    authz.require("report", "read", Some(&id_str), Some(&org_id), None, None)?;
+
+    Ok(Json(run))
+}
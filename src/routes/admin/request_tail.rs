@@ -0,0 +1,193 @@
+//! Live request tail.
+//!
+//! Streams sanitized summaries of API requests as they start and finish,
+//! read from the event bus. Filtering happens server-side so org-scoped
+//! admins only receive their own organization's traffic.
+
+use std::convert::Infallible;
+
+use axum::{
+    Extension,
+    extract::{Query, State},
+    response::{
+        Sse,
+        sse::{Event, KeepAlive},
+    },
+};
+use futures_util::{Stream, stream};
+use serde::Deserialize;
+use tokio::sync::broadcast::error::RecvError;
+use uuid::Uuid;
+
+use super::error::AdminError;
+use crate::{AppState, events::ServerEvent, middleware::AuthzContext};
+
+/// Filters for the request tail. All given filters must match.
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "utoipa", into_params(parameter_in = Query))]
+pub struct RequestTailQuery {
+    /// Only requests attributed to this organization. Required unless the
+    /// caller can read usage for all organizations.
+    pub org_id: Option<Uuid>,
+    /// Only requests served by this provider
+    pub provider: Option<String>,
+    /// Only requests for this model
+    pub model: Option<String>,
+}
+
+impl RequestTailQuery {
+    /// Whether an event belongs in the tail.
+    ///
+    /// Started events don't know their provider or model yet, so they are
+    /// left out when filtering on either.
+    fn matches(&self, event: &ServerEvent) -> bool {
+        match event {
+            ServerEvent::RequestStarted { org_id, .. } => {
+                self.provider.is_none()
+                    && self.model.is_none()
+                    && self.org_id.is_none_or(|id| *org_id == Some(id))
+            }
+            ServerEvent::RequestCompleted {
+                org_id,
+                provider,
+                model,
+                ..
+            } => {
+                self.org_id.is_none_or(|id| *org_id == Some(id))
+                    && self
+                        .provider
+                        .as_ref()
+                        .is_none_or(|p| provider.as_ref() == Some(p))
+                    && self
+                        .model
+                        .as_ref()
+                        .is_none_or(|m| model.as_ref() == Some(m))
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Tail API requests in real time
+///
+/// Server-sent events stream with one `request_started` event when a request
+/// passes authentication and one `request_completed` event when its response
+/// is sent. Summaries carry model, provider, attribution, status, latency,
+/// tokens and cost, never request or response content. Streamed responses
+/// complete when their headers are sent, so they report no tokens or cost.
+/// A `lagged` event reports how many events a slow client missed.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/requests/tail",
+    tag = "observability",
+    params(RequestTailQuery),
+    responses(
+        (status = 200, description = "Server-sent event stream of request summaries", content_type = "text/event-stream"),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn tail(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Query(query): Query<RequestTailQuery>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, AdminError> {
+    let org_id = query.org_id.map(|id| id.to_string());
+    authz.require("usage", "read", None, org_id.as_deref(), None, None)?;
+
+    let rx = state.event_bus.subscribe();
+    let events = stream::unfold((rx, query), |(mut rx, query)| async move {
+        loop {
+            let event = match rx.recv().await {
+                Ok(event) if query.matches(&event) => Event::default()
+                    .event(event.event_type())
+                    .json_data(&event)
+                    .unwrap_or_default(),
+                Ok(_) => continue,
+                Err(RecvError::Lagged(missed)) => {
+                    Event::default().event("lagged").data(missed.to_string())
+                }
+                Err(RecvError::Closed) => return None,
+            };
+            return Some((Ok(event), (rx, query)));
+        }
+    });
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    fn completed(org_id: Option<Uuid>, provider: &str, model: &str) -> ServerEvent {
+        ServerEvent::RequestCompleted {
+            request_id: "req-1".to_string(),
+            timestamp: Utc::now(),
+            method: "POST".to_string(),
+            path: "/v1/chat/completions".to_string(),
+            model: Some(model.to_string()),
+            provider: Some(provider.to_string()),
+            org_id,
+            project_id: None,
+            user_id: None,
+            api_key_id: None,
+            status_code: 200,
+            latency_ms: 120,
+            streamed: false,
+            input_tokens: Some(10),
+            output_tokens: Some(5),
+            cost_microcents: Some(100),
+        }
+    }
+
+    #[test]
+    fn test_tail_filters() {
+        let org = Uuid::new_v4();
+        let started = ServerEvent::RequestStarted {
+            request_id: "req-1".to_string(),
+            timestamp: Utc::now(),
+            method: "POST".to_string(),
+            path: "/v1/chat/completions".to_string(),
+            org_id: Some(org),
+            project_id: None,
+            user_id: None,
+            api_key_id: None,
+        };
+
+        let all = RequestTailQuery::default();
+        assert!(all.matches(&started));
+        assert!(all.matches(&completed(None, "openai", "gpt-4o")));
+
+        let by_org = RequestTailQuery {
+            org_id: Some(org),
+            ..Default::default()
+        };
+        assert!(by_org.matches(&started));
+        assert!(by_org.matches(&completed(Some(org), "openai", "gpt-4o")));
+        assert!(!by_org.matches(&completed(Some(Uuid::new_v4()), "openai", "gpt-4o")));
+        assert!(!by_org.matches(&completed(None, "openai", "gpt-4o")));
+
+        let by_model = RequestTailQuery {
+            provider: Some("openai".to_string()),
+            model: Some("gpt-4o".to_string()),
+            ..Default::default()
+        };
+        assert!(!by_model.matches(&started));
+        assert!(by_model.matches(&completed(None, "openai", "gpt-4o")));
+        assert!(!by_model.matches(&completed(None, "anthropic", "gpt-4o")));
+        assert!(!by_model.matches(&completed(None, "openai", "gpt-4o-mini")));
+
+        // Other event bus traffic never reaches the tail
+        let health = ServerEvent::ProviderHealthChanged {
+            provider: "openai".to_string(),
+            timestamp: Utc::now(),
+            is_healthy: true,
+            latency_ms: None,
+            error_message: None,
+        };
+        assert!(!all.matches(&health));
+    }
+}
@@ -0,0 +1,467 @@
+//! Admin API endpoints for an organization's scheduled tasks.
+//!
+//! A scheduled task sends a prompt to a model on a cron schedule and
+//! delivers the answer to one of the organization's conversations or to a
+//! webhook. The `scheduled_tasks` job runs them; each run is kept in the
+//! task's run history.
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use axum_valid::Valid;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+use super::{AuditActor, error::AdminError};
+use crate::{
+    AppState,
+    config::ScheduledTasksConfig,
+    db::DbPool,
+    middleware::{AdminAuth, AuthzContext, ClientInfo},
+    models::{
+        CreateAuditLog, CreateScheduledTask, Organization, ScheduledTask, ScheduledTaskDelivery,
+        ScheduledTaskRun, UpdateScheduledTask,
+    },
+    routing::route_models_extended,
+    services::{Services, scheduled_tasks},
+    validation::{UrlValidationOptions, validate_base_url_opts},
+};
+
+fn get_services(state: &AppState) -> Result<&Services, AdminError> {
+    state.services.as_ref().ok_or(AdminError::ServicesRequired)
+}
+
+fn get_db(state: &AppState) -> Result<&DbPool, AdminError> {
+    state.db.as_deref().ok_or(AdminError::DatabaseRequired)
+}
+
+fn get_config(state: &AppState) -> Result<&ScheduledTasksConfig, AdminError> {
+    let config = &state.config.features.scheduled_tasks;
+    if !config.enabled {
+        return Err(AdminError::NotConfigured(
+            "Scheduled tasks are not enabled".to_string(),
+        ));
+    }
+    Ok(config)
+}
+
+async fn get_org(state: &AppState, org_slug: &str) -> Result<Organization, AdminError> {
+    get_services(state)?
+        .organizations
+        .get_by_slug(org_slug)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("Organization '{}' not found", org_slug)))
+}
+
+/// Load a scheduled task of `org`. Other organizations' tasks are reported
+/// as missing.
+async fn get_task(
+    db: &DbPool,
+    org: &Organization,
+    task_id: Uuid,
+) -> Result<ScheduledTask, AdminError> {
+    db.scheduled_tasks()
+        .get(task_id)
+        .await?
+        .filter(|task| task.org_id == org.id)
+        .ok_or_else(|| AdminError::NotFound(format!("Scheduled task '{task_id}' not found")))
+}
+
+/// Check a schedule against `min_interval_secs`, returning its first run.
+fn validate_schedule(
+    config: &ScheduledTasksConfig,
+    schedule: &str,
+) -> Result<DateTime<Utc>, AdminError> {
+    scheduled_tasks::validate_schedule(schedule, config.min_interval_secs, Utc::now())
+        .map_err(|e| AdminError::Validation(format!("Invalid schedule: {e}")))
+}
+
+fn validate_model(state: &AppState, model: &str) -> Result<(), AdminError> {
+    route_models_extended(Some(model), None, &state.config.providers)
+        .map(|_| ())
+        .map_err(|e| AdminError::Validation(format!("Invalid model: {e}")))
+}
+
+/// Check that a conversation target belongs to `org` and that a webhook URL
+/// doesn't point at internal addresses.
+async fn validate_delivery(
+    state: &AppState,
+    org: &Organization,
+    delivery: &ScheduledTaskDelivery,
+) -> Result<(), AdminError> {
+    match delivery {
+        ScheduledTaskDelivery::Conversation { conversation_id } => {
+            get_services(state)?
+                .conversations
+                .get_by_id_and_org(*conversation_id, org.id)
+                .await?
+                .ok_or_else(|| {
+                    AdminError::Validation(format!(
+                        "Conversation '{conversation_id}' not found in this organization"
+                    ))
+                })?;
+        }
+        ScheduledTaskDelivery::Webhook { url, .. } => {
+            validate_base_url_opts(
+                url,
+                UrlValidationOptions {
+                    allow_loopback: state.config.server.allow_loopback_urls,
+                    allow_private: state.config.server.allow_private_urls,
+                },
+            )
+            .map_err(|e| AdminError::Validation(format!("Invalid webhook URL: {e}")))?;
+        }
+    }
+    Ok(())
+}
+
+async fn audit(
+    state: &AppState,
+    admin_auth: &AdminAuth,
+    client_info: ClientInfo,
+    action: &str,
+    task: &ScheduledTask,
+) {
+    let Some(services) = &state.services else {
+        return;
+    };
+    let actor = AuditActor::from(admin_auth);
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: format!("scheduled_task.{action}"),
+            resource_type: "scheduled_task".to_string(),
+            resource_id: task.id,
+            org_id: Some(task.org_id),
+            project_id: None,
+            details: json!({
+                "name": task.name,
+                "schedule": task.schedule,
+                "model": task.model,
+                "delivery": task.delivery.as_str(),
+                "enabled": task.enabled,
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+}
+
+/// List of an organization's scheduled tasks, ordered by name
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ScheduledTaskListResponse {
+    pub data: Vec<ScheduledTask>,
+}
+
+/// List an organization's scheduled tasks
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/organizations/{org_slug}/scheduled-tasks",
+    tag = "scheduled-tasks",
+    operation_id = "scheduled_task_list",
+    params(("org_slug" = String, Path, description = "Organization slug")),
+    responses(
+        (status = 200, description = "The organization's scheduled tasks", body = ScheduledTaskListResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "Scheduled tasks are not enabled", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn list(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(org_slug): Path<String>,
+) -> Result<Json<ScheduledTaskListResponse>, AdminError> {
+    get_config(&state)?;
+    let org = get_org(&state, &org_slug).await?;
+    authz.require(
+        "scheduled_task",
+        "list",
+        None,
+        Some(&org.id.to_string()),
+        None,
+        None,
+    )?;
+    let db = get_db(&state)?;
+    let data = db.scheduled_tasks().list_by_org(org.id).await?;
+    Ok(Json(ScheduledTaskListResponse { data }))
+}
+
+/// Create a scheduled task
+///
+/// The schedule may not run more often than `[features.scheduled_tasks]
+/// min_interval_secs`. Conversation targets must belong to the organization.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/organizations/{org_slug}/scheduled-tasks",
+    tag = "scheduled-tasks",
+    operation_id = "scheduled_task_create",
+    params(("org_slug" = String, Path, description = "Organization slug")),
+    request_body = CreateScheduledTask,
+    responses(
+        (status = 201, description = "Scheduled task created", body = ScheduledTask),
+        (status = 400, description = "Invalid schedule, model or delivery target", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found", body = crate::openapi::ErrorResponse),
+        (status = 409, description = "Task with same name already exists, or task limit reached", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "Scheduled tasks are not enabled", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn create(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path(org_slug): Path<String>,
+    Valid(Json(input)): Valid<Json<CreateScheduledTask>>,
+) -> Result<(StatusCode, Json<ScheduledTask>), AdminError> {
+    let config = get_config(&state)?;
+    let org = get_org(&state, &org_slug).await?;
+    authz.require(
+        "scheduled_task",
+        "create",
+        None,
+        Some(&org.id.to_string()),
+        None,
+        None,
+    )?;
+    let db = get_db(&state)?;
+
+    let max = state
+        .config
+        .limits
+        .resource_limits
+        .max_scheduled_tasks_per_org;
+    if max > 0 {
+        let count = db.scheduled_tasks().count_by_org(org.id).await?;
+        if count >= max as i64 {
+            return Err(AdminError::Conflict(format!(
+                "Organization has reached the maximum number of scheduled tasks ({max})"
+            )));
+        }
+    }
+
+    let first_run = validate_schedule(config, &input.schedule)?;
+    validate_model(&state, &input.model)?;
+    validate_delivery(&state, &org, &input.delivery).await?;
+
+    let next_run_at = input.enabled.then_some(first_run);
+    let task = db
+        .scheduled_tasks()
+        .create(org.id, input, next_run_at)
+        .await?;
+
+    audit(&state, &admin_auth, client_info, "create", &task).await;
+    Ok((StatusCode::CREATED, Json(task)))
+}
+
+/// Get a scheduled task
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/organizations/{org_slug}/scheduled-tasks/{task_id}",
+    tag = "scheduled-tasks",
+    operation_id = "scheduled_task_get",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("task_id" = Uuid, Path, description = "Scheduled task ID"),
+    ),
+    responses(
+        (status = 200, description = "The scheduled task", body = ScheduledTask),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Scheduled task not found", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "Scheduled tasks are not enabled", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn get(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path((org_slug, task_id)): Path<(String, Uuid)>,
+) -> Result<Json<ScheduledTask>, AdminError> {
+    get_config(&state)?;
+    let org = get_org(&state, &org_slug).await?;
+    authz.require(
+        "scheduled_task",
+        "read",
+        Some(&task_id.to_string()),
+        Some(&org.id.to_string()),
+        None,
+        None,
+    )?;
+    let db = get_db(&state)?;
+    Ok(Json(get_task(db, &org, task_id).await?))
+}
+
+/// Update a scheduled task
+///
+/// Omitted fields are left unchanged. The next run is recomputed from the
+/// schedule; enabling a task also resets its failure count.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    patch,
+    path = "/admin/v1/organizations/{org_slug}/scheduled-tasks/{task_id}",
+    tag = "scheduled-tasks",
+    operation_id = "scheduled_task_update",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("task_id" = Uuid, Path, description = "Scheduled task ID"),
+    ),
+    request_body = UpdateScheduledTask,
+    responses(
+        (status = 200, description = "Scheduled task updated", body = ScheduledTask),
+        (status = 400, description = "Invalid schedule, model or delivery target", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Scheduled task not found", body = crate::openapi::ErrorResponse),
+        (status = 409, description = "Task with same name already exists", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "Scheduled tasks are not enabled", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn update(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path((org_slug, task_id)): Path<(String, Uuid)>,
+    Valid(Json(input)): Valid<Json<UpdateScheduledTask>>,
+) -> Result<Json<ScheduledTask>, AdminError> {
+    let config = get_config(&state)?;
+    let org = get_org(&state, &org_slug).await?;
+    authz.require(
+        "scheduled_task",
+        "update",
+        Some(&task_id.to_string()),
+        Some(&org.id.to_string()),
+        None,
+        None,
+    )?;
+    let db = get_db(&state)?;
+    let existing = get_task(db, &org, task_id).await?;
+
+    let schedule = input.schedule.as_deref().unwrap_or(&existing.schedule);
+    let first_run = validate_schedule(config, schedule)?;
+    if let Some(model) = &input.model {
+        validate_model(&state, model)?;
+    }
+    if let Some(delivery) = &input.delivery {
+        validate_delivery(&state, &org, delivery).await?;
+    }
+
+    let enabled = input.enabled.unwrap_or(existing.enabled);
+    let next_run_at = enabled.then_some(first_run);
+    let task = db
+        .scheduled_tasks()
+        .update(task_id, input, next_run_at)
+        .await?;
+
+    audit(&state, &admin_auth, client_info, "update", &task).await;
+    Ok(Json(task))
+}
+
+/// Delete a scheduled task
+///
+/// The task's run history is deleted with it.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    delete,
+    path = "/admin/v1/organizations/{org_slug}/scheduled-tasks/{task_id}",
+    tag = "scheduled-tasks",
+    operation_id = "scheduled_task_delete",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("task_id" = Uuid, Path, description = "Scheduled task ID"),
+    ),
+    responses(
+        (status = 204, description = "Scheduled task deleted"),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Scheduled task not found", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "Scheduled tasks are not enabled", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn delete(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path((org_slug, task_id)): Path<(String, Uuid)>,
+) -> Result<StatusCode, AdminError> {
+    get_config(&state)?;
+    let org = get_org(&state, &org_slug).await?;
+    authz.require(
+        "scheduled_task",
+        "delete",
+        Some(&task_id.to_string()),
+        Some(&org.id.to_string()),
+        None,
+        None,
+    )?;
+    let db = get_db(&state)?;
+    let task = get_task(db, &org, task_id).await?;
+    db.scheduled_tasks().delete(task_id).await?;
+
+    audit(&state, &admin_auth, client_info, "delete", &task).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Query parameters for listing a task's runs
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::IntoParams))]
+pub struct ListRunsQuery {
+    /// Maximum number of runs to return (default 20, max 100)
+    pub limit: Option<i64>,
+}
+
+/// A scheduled task's runs, newest first
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ScheduledTaskRunListResponse {
+    pub data: Vec<ScheduledTaskRun>,
+}
+
+/// List a scheduled task's runs
+///
+/// Only the newest `[features.scheduled_tasks] run_history_limit` runs are
+/// kept.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/organizations/{org_slug}/scheduled-tasks/{task_id}/runs",
+    tag = "scheduled-tasks",
+    operation_id = "scheduled_task_list_runs",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("task_id" = Uuid, Path, description = "Scheduled task ID"),
+        ListRunsQuery,
+    ),
+    responses(
+        (status = 200, description = "The task's runs, newest first", body = ScheduledTaskRunListResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Scheduled task not found", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "Scheduled tasks are not enabled", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn list_runs(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path((org_slug, task_id)): Path<(String, Uuid)>,
+    Query(query): Query<ListRunsQuery>,
+) -> Result<Json<ScheduledTaskRunListResponse>, AdminError> {
+    get_config(&state)?;
+    let org = get_org(&state, &org_slug).await?;
+    authz.require(
+        "scheduled_task",
+        "read",
+        Some(&task_id.to_string()),
+        Some(&org.id.to_string()),
+        None,
+        None,
+    )?;
+    let db = get_db(&state)?;
+    get_task(db, &org, task_id).await?;
+
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let data = db.scheduled_tasks().list_runs(task_id, limit).await?;
+    Ok(Json(ScheduledTaskRunListResponse { data }))
+}
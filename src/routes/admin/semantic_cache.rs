@@ -0,0 +1,265 @@
+//! Admin endpoints for inspecting and purging an organization's semantic cache.
+//!
+//! Semantic cache entries are namespaced by the requesting organization and
+//! project (see [`crate::cache::SemanticCache`]), so they can be inspected and
+//! invalidated per organization without touching other tenants.
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+};
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::{AuditActor, error::AdminError};
+use crate::{
+    AppState,
+    cache::{SIMILARITY_BUCKETS, SemanticCache, SemanticCacheStats},
+    middleware::{AdminAuth, AuthzContext, ClientInfo},
+    models::CreateAuditLog,
+};
+
+/// Default and maximum number of entries returned by the inspect endpoint.
+const DEFAULT_ENTRY_LIMIT: usize = 50;
+const MAX_ENTRY_LIMIT: usize = 500;
+
+/// Query parameters for inspecting an organization's semantic cache.
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema, utoipa::IntoParams))]
+pub struct SemanticCacheQuery {
+    /// Maximum number of entries to return, newest first (default: 50, max: 500).
+    pub limit: Option<usize>,
+}
+
+/// A semantic cache entry.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct SemanticCacheEntry {
+    /// Exact-match cache key of the cached response
+    pub cache_key: String,
+    /// Model the response was generated by
+    pub model: String,
+    /// Project the entry is scoped to, if any
+    pub project_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Semantic hits within a similarity range.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct SimilarityBucket {
+    /// Inclusive lower bound of the range
+    pub min_similarity: f64,
+    /// Exclusive upper bound, absent for the last bucket
+    pub max_similarity: Option<f64>,
+    pub hits: u64,
+}
+
+/// Lookup statistics for an organization since its last purge.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct SemanticCacheStatsResponse {
+    pub lookups: u64,
+    pub exact_hits: u64,
+    pub semantic_hits: u64,
+    pub misses: u64,
+    /// Fraction of lookups served from cache; absent when there were none
+    pub hit_rate: Option<f64>,
+    /// Configured minimum similarity for a semantic hit
+    pub similarity_threshold: f64,
+    /// Similarity scores of semantic hits
+    pub similarity_distribution: Vec<SimilarityBucket>,
+}
+
+impl SemanticCacheStatsResponse {
+    fn new(stats: SemanticCacheStats, similarity_threshold: f64) -> Self {
+        let similarity_distribution = SIMILARITY_BUCKETS
+            .iter()
+            .enumerate()
+            .map(|(i, &min_similarity)| SimilarityBucket {
+                min_similarity,
+                max_similarity: SIMILARITY_BUCKETS.get(i + 1).copied(),
+                hits: stats.similarity_buckets.get(i).copied().unwrap_or(0),
+            })
+            .collect();
+        Self {
+            lookups: stats.lookups(),
+            exact_hits: stats.exact_hits,
+            semantic_hits: stats.semantic_hits,
+            misses: stats.misses,
+            hit_rate: stats.hit_rate(),
+            similarity_threshold,
+            similarity_distribution,
+        }
+    }
+}
+
+/// An organization's semantic cache contents and statistics.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct SemanticCacheResponse {
+    /// Number of unexpired entries
+    pub entry_count: u64,
+    /// Most recent entries, newest first
+    pub entries: Vec<SemanticCacheEntry>,
+    pub stats: SemanticCacheStatsResponse,
+}
+
+/// Result of purging an organization's semantic cache.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct SemanticCachePurgeResponse {
+    /// Number of entries deleted, along with their cached responses
+    pub deleted_entries: u64,
+}
+
+fn get_semantic_cache(state: &AppState) -> Result<&SemanticCache, AdminError> {
+    state
+        .semantic_cache
+        .as_deref()
+        .ok_or_else(|| AdminError::NotConfigured("Semantic caching is not enabled".to_string()))
+}
+
+/// Inspect an organization's semantic cache
+///
+/// Returns the organization's most recent entries and its lookup statistics:
+/// exact and semantic hits, misses, hit rate and the similarity distribution
+/// of semantic hits.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/organizations/{slug}/semantic-cache",
+    tag = "organizations",
+    operation_id = "org_semantic_cache_get",
+    params(
+        ("slug" = String, Path, description = "Organization slug"),
+        SemanticCacheQuery,
+    ),
+    responses(
+        (status = 200, description = "Semantic cache contents and statistics", body = SemanticCacheResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "Semantic caching is not enabled", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.semantic_cache.get", skip(state, authz, query), fields(%slug))]
+pub async fn get(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(slug): Path<String>,
+    Query(query): Query<SemanticCacheQuery>,
+) -> Result<Json<SemanticCacheResponse>, AdminError> {
+    let services = state
+        .services
+        .as_ref()
+        .ok_or(AdminError::ServicesRequired)?;
+    let semantic_cache = get_semantic_cache(&state)?;
+
+    let org = services
+        .organizations
+        .get_by_slug(&slug)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("Organization '{}' not found", slug)))?;
+    let org_id = org.id.to_string();
+    authz.require("semantic_cache", "read", None, Some(&org_id), None, None)?;
+
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_ENTRY_LIMIT)
+        .clamp(1, MAX_ENTRY_LIMIT);
+    let internal = |e: crate::cache::SemanticCacheError| AdminError::Internal(e.to_string());
+    let entry_count = semantic_cache
+        .count_organization_entries(&org_id)
+        .await
+        .map_err(internal)?;
+    let entries = semantic_cache
+        .list_organization_entries(&org_id, limit)
+        .await
+        .map_err(internal)?;
+    let stats = semantic_cache
+        .organization_stats(&org_id)
+        .await
+        .map_err(internal)?;
+
+    let timestamp = |secs: i64| Utc.timestamp_opt(secs, 0).single().unwrap_or_default();
+    Ok(Json(SemanticCacheResponse {
+        entry_count,
+        entries: entries
+            .into_iter()
+            .map(|m| SemanticCacheEntry {
+                cache_key: m.cache_key,
+                model: m.model,
+                project_id: m.project_id,
+                created_at: timestamp(m.created_at),
+                expires_at: timestamp(m.created_at.saturating_add(m.ttl_secs as i64)),
+            })
+            .collect(),
+        stats: SemanticCacheStatsResponse::new(stats, semantic_cache.similarity_threshold()),
+    }))
+}
+
+/// Purge an organization's semantic cache
+///
+/// Deletes every semantic cache entry for the organization, the cached
+/// responses they point at, and its lookup statistics. Other organizations
+/// are unaffected.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    delete,
+    path = "/admin/v1/organizations/{slug}/semantic-cache",
+    tag = "organizations",
+    operation_id = "org_semantic_cache_purge",
+    params(("slug" = String, Path, description = "Organization slug")),
+    responses(
+        (status = 200, description = "Semantic cache purged", body = SemanticCachePurgeResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "Semantic caching is not enabled", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.semantic_cache.purge", skip(state, admin_auth, authz), fields(%slug))]
+pub async fn purge(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path(slug): Path<String>,
+) -> Result<Json<SemanticCachePurgeResponse>, AdminError> {
+    let services = state
+        .services
+        .as_ref()
+        .ok_or(AdminError::ServicesRequired)?;
+    let semantic_cache = get_semantic_cache(&state)?;
+    let actor = AuditActor::from(&admin_auth);
+
+    let org = services
+        .organizations
+        .get_by_slug(&slug)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("Organization '{}' not found", slug)))?;
+    let org_id = org.id.to_string();
+    authz.require("semantic_cache", "delete", None, Some(&org_id), None, None)?;
+
+    let deleted_entries = semantic_cache
+        .purge_organization(&org_id)
+        .await
+        .map_err(|e| AdminError::Internal(e.to_string()))?;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "semantic_cache.purge".to_string(),
+            resource_type: "organization".to_string(),
+            resource_id: org.id,
+            org_id: Some(org.id),
+            project_id: None,
+            details: json!({ "deleted_entries": deleted_entries }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(Json(SemanticCachePurgeResponse { deleted_entries }))
+}
@@ -3,23 +3,26 @@
 //! These endpoints enable the critical enterprise use case:
 //! "An employee was terminated. Force logout all their sessions immediately."
 //!
-//! Sessions are nested under users: `/admin/v1/users/{user_id}/sessions`
+//! Sessions are nested under users: `/admin/v1/users/{user_id}/sessions`.
+//! Org admins can also revoke all sessions of their organization's members
+//! via `/admin/v1/organizations/{slug}/force-logout`.
 
 use axum::{
     Extension, Json,
     extract::{Path, State},
 };
 use chrono::{DateTime, Utc};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use uuid::Uuid;
 
 use super::{AuditActor, error::AdminError};
 use crate::{
     AppState,
-    auth::session_store::{DeviceInfo, SharedSessionStore},
+    auth::session_store::{DeviceInfo, SharedSessionStore, revoke_user_sessions},
+    db::ListParams,
     middleware::{AdminAuth, AuthzContext, ClientInfo},
-    models::CreateAuditLog,
+    models::{CreateAuditLog, User},
     services::Services,
 };
 
@@ -62,6 +65,29 @@ pub struct SessionsRevokedResponse {
     pub sessions_revoked: usize,
 }
 
+/// Request to force logout members of an organization.
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ForceLogoutRequest {
+    /// Member to log out. When omitted, every member of the organization is
+    /// logged out.
+    #[serde(default)]
+    pub user_id: Option<Uuid>,
+}
+
+/// Response after an organization-wide force logout.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ForceLogoutResponse {
+    /// Number of users that had at least one session revoked
+    pub users_affected: usize,
+    /// Total number of sessions revoked
+    pub sessions_revoked: usize,
+    /// Whether enhanced session management is enabled.
+    /// If false, sessions cannot be enumerated and nothing was revoked.
+    pub enhanced_enabled: bool,
+}
+
 fn get_services(state: &AppState) -> Result<&Services, AdminError> {
     state.services.as_ref().ok_or(AdminError::ServicesRequired)
 }
@@ -362,6 +388,192 @@ pub async fn delete_one(
     Ok(Json(SessionsRevokedResponse { sessions_revoked }))
 }
 
+/// Force logout organization members.
+///
+/// Revokes every session of a member, or of every member when `user_id` is
+/// omitted. The body is optional; a bare POST logs out the whole
+/// organization. Returns 0 sessions revoked if enhanced session management
+/// is not enabled.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/organizations/{org_slug}/force-logout",
+    tag = "users",
+    operation_id = "org_force_logout",
+    params(("org_slug" = String, Path, description = "Organization slug")),
+    request_body = ForceLogoutRequest,
+    responses(
+        (status = 200, description = "Sessions revoked", body = ForceLogoutResponse),
+        (status = 400, description = "No session store configured", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found or user is not a member", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.sessions.force_logout", skip(state, admin_auth, authz, request), fields(%org_slug))]
+pub async fn force_logout(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path(org_slug): Path<String>,
+    request: Option<Json<ForceLogoutRequest>>,
+) -> Result<Json<ForceLogoutResponse>, AdminError> {
+    let request = request.map(|Json(request)| request).unwrap_or_default();
+    let services = get_services(&state)?;
+    let actor = AuditActor::from(&admin_auth);
+
+    let org = services
+        .organizations
+        .get_by_slug(&org_slug)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("Organization '{}' not found", org_slug)))?;
+    let org_id_str = org.id.to_string();
+
+    let users: Vec<User> = match request.user_id {
+        Some(user_id) => {
+            authz.require(
+                "user",
+                "manage",
+                Some(&user_id.to_string()),
+                Some(&org_id_str),
+                None,
+                None,
+            )?;
+
+            let is_member = services
+                .users
+                .get_org_memberships_for_user(user_id)
+                .await?
+                .iter()
+                .any(|m| m.org_id == org.id);
+            let user = services.users.get_by_id(user_id).await?;
+            match user {
+                Some(user) if is_member => vec![user],
+                _ => {
+                    return Err(AdminError::NotFound(format!(
+                        "User '{}' is not a member of organization '{}'",
+                        user_id, org_slug
+                    )));
+                }
+            }
+        }
+        None => {
+            // Logging out a whole organization is an org-level operation
+            authz.require(
+                "organization",
+                "update",
+                Some(&org_id_str),
+                Some(&org_id_str),
+                None,
+                None,
+            )?;
+
+            let mut members = Vec::new();
+            let mut params = ListParams {
+                limit: Some(500),
+                ..Default::default()
+            };
+            loop {
+                let page = services
+                    .users
+                    .list_org_members(org.id, params.clone())
+                    .await?;
+                members.extend(page.items);
+                match page.cursors.next {
+                    Some(next) if page.has_more => params.cursor = Some(next),
+                    _ => break,
+                }
+            }
+            members
+        }
+    };
+
+    let session_store = get_session_store(&state)?;
+    let enhanced_enabled = session_store.is_enhanced_enabled();
+
+    let mut users_affected = 0;
+    let mut sessions_revoked = 0;
+    for user in &users {
+        // API-only users have no browser sessions
+        if user.external_id.is_empty() {
+            continue;
+        }
+
+        let revoked = revoke_user_sessions(session_store.as_ref(), &user.external_id)
+            .await
+            .map_err(|e| AdminError::Internal(format!("Failed to revoke sessions: {}", e)))?;
+
+        // Single-user requests are always audited; org-wide requests only
+        // record the members that actually lost a session
+        if revoked.is_empty() && request.user_id.is_none() {
+            continue;
+        }
+        if !revoked.is_empty() {
+            users_affected += 1;
+            sessions_revoked += revoked.len();
+        }
+
+        let session_ids: Vec<Uuid> = revoked.iter().map(|s| s.id).collect();
+        let _ = services
+            .audit_logs
+            .create(CreateAuditLog {
+                actor_type: actor.actor_type,
+                actor_id: actor.actor_id,
+                action: "session.force_logout".to_string(),
+                resource_type: "user".to_string(),
+                resource_id: user.id,
+                org_id: Some(org.id),
+                project_id: None,
+                details: json!({
+                    "org_slug": org_slug,
+                    "user_email": user.email,
+                    "external_id": user.external_id,
+                    "sessions_revoked": revoked.len(),
+                    "session_ids": session_ids,
+                }),
+                ip_address: client_info.ip_address.clone(),
+                user_agent: client_info.user_agent.clone(),
+            })
+            .await;
+    }
+
+    if request.user_id.is_none() {
+        let _ = services
+            .audit_logs
+            .create(CreateAuditLog {
+                actor_type: actor.actor_type,
+                actor_id: actor.actor_id,
+                action: "session.force_logout_org".to_string(),
+                resource_type: "organization".to_string(),
+                resource_id: org.id,
+                org_id: Some(org.id),
+                project_id: None,
+                details: json!({
+                    "org_slug": org_slug,
+                    "members": users.len(),
+                    "users_affected": users_affected,
+                    "sessions_revoked": sessions_revoked,
+                }),
+                ip_address: client_info.ip_address,
+                user_agent: client_info.user_agent,
+            })
+            .await;
+    }
+
+    tracing::info!(
+        org_id = %org.id,
+        user_id = ?request.user_id,
+        users_affected,
+        sessions_revoked,
+        "Force logout: revoked organization sessions"
+    );
+
+    Ok(Json(ForceLogoutResponse {
+        users_affected,
+        sessions_revoked,
+        enhanced_enabled,
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -406,4 +618,29 @@ mod tests {
         let json = serde_json::to_string(&response).unwrap();
         assert!(json.contains("\"sessions_revoked\":3"));
     }
+
+    #[test]
+    fn test_force_logout_request_user_id_optional() {
+        let request: ForceLogoutRequest = serde_json::from_str("{}").unwrap();
+        assert!(request.user_id.is_none());
+
+        let user_id = Uuid::new_v4();
+        let request: ForceLogoutRequest =
+            serde_json::from_value(json!({ "user_id": user_id })).unwrap();
+        assert_eq!(request.user_id, Some(user_id));
+    }
+
+    #[test]
+    fn test_force_logout_response_serialization() {
+        let response = ForceLogoutResponse {
+            users_affected: 2,
+            sessions_revoked: 5,
+            enhanced_enabled: true,
+        };
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["users_affected"], 2);
+        assert_eq!(json["sessions_revoked"], 5);
+        assert_eq!(json["enhanced_enabled"], true);
+    }
 }
@@ -0,0 +1,383 @@
+//! Admin API endpoints for shadow traffic results.
+//!
+//! Results are recorded by the rules in `[features.shadow_traffic]`; see
+//! [`crate::services::shadow_traffic`] for how requests are mirrored.
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+use super::{AuditActor, error::AdminError};
+use crate::{
+    AppState,
+    db::{Cursor, CursorDirection, ListParams},
+    middleware::{AdminAuth, AuthzContext, ClientInfo},
+    models::{CreateAuditLog, ShadowReportRow, ShadowResult, ShadowResultFilter},
+    openapi::PaginationMeta,
+    services::{
+        ShadowResultService,
+        replay::{DiffLine, diff_lines},
+    },
+};
+
+/// Filter shared by the list, report and delete endpoints.
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema, utoipa::IntoParams))]
+pub struct ShadowResultFilterQuery {
+    /// Filter by rule name from `[features.shadow_traffic]`.
+    pub rule: Option<String>,
+    /// Filter by organization ID.
+    pub org_id: Option<Uuid>,
+    /// Only results recorded at or after this time (RFC3339).
+    pub from: Option<DateTime<Utc>>,
+    /// Only results recorded before this time (RFC3339).
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl From<ShadowResultFilterQuery> for ShadowResultFilter {
+    fn from(query: ShadowResultFilterQuery) -> Self {
+        Self {
+            rule: query.rule,
+            org_id: query.org_id,
+            from: query.from,
+            to: query.to,
+        }
+    }
+}
+
+/// Query parameters for listing shadow results.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema, utoipa::IntoParams))]
+pub struct ShadowResultListQuery {
+    /// Filter by rule name from `[features.shadow_traffic]`.
+    pub rule: Option<String>,
+    /// Filter by organization ID.
+    pub org_id: Option<Uuid>,
+    /// Only results recorded at or after this time (RFC3339).
+    pub from: Option<DateTime<Utc>>,
+    /// Only results recorded before this time (RFC3339).
+    pub to: Option<DateTime<Utc>>,
+    /// Maximum number of results to return (default: 100).
+    pub limit: Option<i64>,
+    /// Cursor for keyset pagination. Encoded as base64 string.
+    #[cfg_attr(
+        feature = "utoipa",
+        schema(example = "MTczMzU4MDgwMDAwMDphYmMxMjM0NS02Nzg5LTAxMjMtNDU2Ny0wMTIzNDU2Nzg5YWI")
+    )]
+    pub cursor: Option<String>,
+    /// Pagination direction: "forward" (default) or "backward".
+    #[serde(default)]
+    pub direction: Option<String>,
+}
+
+impl ShadowResultListQuery {
+    /// Split into repository filter and list params, rejecting invalid cursors.
+    fn try_into_parts(self) -> Result<(ShadowResultFilter, ListParams), AdminError> {
+        let cursor = match &self.cursor {
+            Some(c) => Some(
+                Cursor::decode(c)
+                    .map_err(|e| AdminError::BadRequest(format!("Invalid cursor: {}", e)))?,
+            ),
+            None => None,
+        };
+
+        let direction = match self.direction.as_deref() {
+            Some("backward") => CursorDirection::Backward,
+            Some("forward") | None => CursorDirection::Forward,
+            Some(other) => {
+                return Err(AdminError::BadRequest(format!(
+                    "Invalid direction '{}': must be 'forward' or 'backward'",
+                    other
+                )));
+            }
+        };
+
+        let filter = ShadowResultFilter {
+            rule: self.rule,
+            org_id: self.org_id,
+            from: self.from,
+            to: self.to,
+        };
+        let params = ListParams {
+            limit: Some(self.limit.unwrap_or(100)),
+            cursor,
+            direction,
+            ..Default::default()
+        }
+        .clamp();
+        Ok((filter, params))
+    }
+}
+
+/// Paginated list of shadow results
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ShadowResultListResponse {
+    /// Shadow results, newest first
+    pub data: Vec<ShadowResult>,
+    /// Pagination metadata
+    pub pagination: PaginationMeta,
+}
+
+/// Shadow traffic comparison per rule and provider/model pair
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ShadowReportResponse {
+    pub data: Vec<ShadowReportRow>,
+}
+
+/// A shadow result with a line diff of the two outputs
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ShadowResultDetail {
+    #[serde(flatten)]
+    pub result: ShadowResult,
+    /// Line diff from the primary output to the shadow output
+    pub diff: Vec<DiffLine>,
+}
+
+/// Result of deleting shadow results
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ShadowResultDeleteResponse {
+    /// Number of results deleted
+    pub deleted: u64,
+}
+
+fn get_service(state: &AppState) -> Result<&ShadowResultService, AdminError> {
+    state
+        .services
+        .as_ref()
+        .map(|s| &s.shadow_results)
+        .ok_or(AdminError::ServicesRequired)
+}
+
+/// Pin `filter` to the caller's organization when they are an org member.
+///
+/// Mirrored requests carry prompts and outputs, so org members only see
+/// their own organization's results.
+fn scope_to_membership(
+    authz: &AuthzContext,
+    filter: &mut ShadowResultFilter,
+    action: &str,
+) -> Result<(), AdminError> {
+    let Some(membership) = authz.subject.org_ids.first() else {
+        return Ok(());
+    };
+    let scoped: Uuid = membership.parse().map_err(|_| {
+        AdminError::Internal(format!(
+            "shadow_result:{action} authz subject has a non-UUID org membership"
+        ))
+    })?;
+    match filter.org_id {
+        Some(requested) if requested != scoped => Err(AdminError::Forbidden(format!(
+            "shadow_result:{action} scoped outside your organization"
+        ))),
+        _ => {
+            filter.org_id = Some(scoped);
+            Ok(())
+        }
+    }
+}
+
+/// List shadow results
+///
+/// Returns mirrored requests newest first, with both outputs as recorded
+/// (truncated to `max_output_bytes`).
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/shadow-results",
+    tag = "shadow-traffic",
+    operation_id = "shadow_result_list",
+    params(ShadowResultListQuery),
+    responses(
+        (status = 200, description = "List of shadow results", body = ShadowResultListResponse),
+        (status = 400, description = "Invalid cursor or direction", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Organization outside caller's scope", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn list(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Query(query): Query<ShadowResultListQuery>,
+) -> Result<Json<ShadowResultListResponse>, AdminError> {
+    let service = get_service(&state)?;
+    let (mut filter, params) = query.try_into_parts()?;
+    let limit = params.limit.unwrap_or(100);
+
+    scope_to_membership(&authz, &mut filter, "list")?;
+    let org_scope = filter.org_id.map(|id| id.to_string());
+    authz.require(
+        "shadow_result",
+        "list",
+        None,
+        org_scope.as_deref(),
+        None,
+        None,
+    )?;
+
+    let result = service.list(&filter, params).await?;
+
+    let pagination = PaginationMeta::with_cursors(
+        limit,
+        result.has_more,
+        result.cursors.next.map(|c| c.encode()),
+        result.cursors.prev.map(|c| c.encode()),
+    );
+
+    Ok(Json(ShadowResultListResponse {
+        data: result.items,
+        pagination,
+    }))
+}
+
+/// Compare primary and shadow providers
+///
+/// Aggregates results per rule and provider/model pair: error counts,
+/// identical outputs, mean similarity, mean latency, output tokens and cost.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/shadow-results/report",
+    tag = "shadow-traffic",
+    operation_id = "shadow_result_report",
+    params(ShadowResultFilterQuery),
+    responses(
+        (status = 200, description = "Comparison report", body = ShadowReportResponse),
+        (status = 403, description = "Organization outside caller's scope", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn report(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Query(query): Query<ShadowResultFilterQuery>,
+) -> Result<Json<ShadowReportResponse>, AdminError> {
+    let service = get_service(&state)?;
+    let mut filter = ShadowResultFilter::from(query);
+
+    scope_to_membership(&authz, &mut filter, "list")?;
+    let org_scope = filter.org_id.map(|id| id.to_string());
+    authz.require(
+        "shadow_result",
+        "list",
+        None,
+        org_scope.as_deref(),
+        None,
+        None,
+    )?;
+
+    let data = service.report(&filter).await?;
+    Ok(Json(ShadowReportResponse { data }))
+}
+
+/// Get a shadow result by ID
+///
+/// Includes a line diff from the primary output to the shadow output.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/shadow-results/{id}",
+    tag = "shadow-traffic",
+    operation_id = "shadow_result_get",
+    params(("id" = Uuid, Path, description = "Shadow result ID")),
+    responses(
+        (status = 200, description = "Shadow result found", body = ShadowResultDetail),
+        (status = 404, description = "Shadow result not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn get(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ShadowResultDetail>, AdminError> {
+    let service = get_service(&state)?;
+
+    let result = service
+        .get_by_id(id)
+        .await?
+        .ok_or_else(|| AdminError::NotFound("Shadow result not found".to_string()))?;
+
+    let id_str = id.to_string();
+    let org_id = result.org_id.map(|id| id.to_string());
+    let project_id = result.project_id.map(|id| id.to_string());
+    authz.require(
+        "shadow_result",
+        "read",
+        Some(&id_str),
+        org_id.as_deref(),
+        None,
+        project_id.as_deref(),
+    )?;
+
+    let diff = diff_lines(&result.primary_output, &result.shadow_output);
+    Ok(Json(ShadowResultDetail { result, diff }))
+}
+
+/// Delete shadow results
+///
+/// Removes every result matching the filter, e.g. all results for a rule
+/// once its migration is done.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    delete,
+    path = "/admin/v1/shadow-results",
+    tag = "shadow-traffic",
+    operation_id = "shadow_result_delete",
+    params(ShadowResultFilterQuery),
+    responses(
+        (status = 200, description = "Results deleted", body = ShadowResultDeleteResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn delete(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Query(query): Query<ShadowResultFilterQuery>,
+) -> Result<Json<ShadowResultDeleteResponse>, AdminError> {
+    let services = state
+        .services
+        .as_ref()
+        .ok_or(AdminError::ServicesRequired)?;
+    let actor = AuditActor::from(&admin_auth);
+    let mut filter = ShadowResultFilter::from(query);
+
+    scope_to_membership(&authz, &mut filter, "delete")?;
+    let org_scope = filter.org_id.map(|id| id.to_string());
+    authz.require(
+        "shadow_result",
+        "delete",
+        None,
+        org_scope.as_deref(),
+        None,
+        None,
+    )?;
+
+    let deleted = services.shadow_results.delete(&filter).await?;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "shadow_result.delete".to_string(),
+            resource_type: "shadow_result".to_string(),
+            resource_id: Uuid::nil(),
+            org_id: filter.org_id,
+            project_id: None,
+            details: json!({
+                "rule": filter.rule,
+                "from": filter.from,
+                "to": filter.to,
+                "deleted": deleted,
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(Json(ShadowResultDeleteResponse { deleted }))
+}
@@ -0,0 +1,311 @@
+//! Self-service step-up authentication endpoints.
+//!
+//! Admins enroll a TOTP authenticator at `/admin/v1/me/step-up/totp` and
+//! verify codes at `/admin/v1/me/step-up/verify` to unlock the high-risk
+//! routes listed in `[auth.step_up].routes`. Enforcement itself lives in the
+//! admin auth middleware.
+
+use axum::{Extension, Json, extract::State, http::StatusCode};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+use super::{AuditActor, error::AdminError};
+use crate::{
+    AppState,
+    auth::totp,
+    middleware::{
+        AdminAuth, AuthzContext, ClientInfo,
+        util::step_up::{self, TotpAttempt},
+    },
+    models::CreateAuditLog,
+    services::{Services, audit_logs::auth_events},
+};
+
+fn get_services(state: &AppState) -> Result<&Services, AdminError> {
+    state.services.as_ref().ok_or(AdminError::ServicesRequired)
+}
+
+fn get_user_id(admin_auth: &AdminAuth) -> Result<Uuid, AdminError> {
+    admin_auth
+        .identity
+        .user_id
+        .ok_or(AdminError::Forbidden("User account required".to_string()))
+}
+
+/// Current user's step-up state
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct StepUpStatus {
+    /// Whether step-up is enforced on protected routes
+    pub enforced: bool,
+    /// Whether a TOTP authenticator is enrolled and confirmed
+    pub totp_enrolled: bool,
+    /// Whether an enrollment was started but not yet confirmed
+    pub enrollment_pending: bool,
+    /// Until when the last TOTP verification satisfies step-up
+    pub verified_until: Option<DateTime<Utc>>,
+}
+
+/// A started TOTP enrollment. The secret is shown only once.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct TotpEnrollmentResponse {
+    /// Base32 shared secret, for manual entry
+    pub secret: String,
+    /// `otpauth://` URI to render as a QR code
+    pub otpauth_uri: String,
+}
+
+/// A 6-digit code from the authenticator app
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct TotpCodeRequest {
+    pub code: String,
+}
+
+/// Result of a successful step-up verification
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct StepUpVerifyResponse {
+    /// Until when protected routes are unlocked. `null` when no cache is
+    /// configured; send codes per request in `X-Step-Up-TOTP` instead.
+    pub verified_until: Option<DateTime<Utc>>,
+}
+
+async fn build_status(
+    state: &AppState,
+    services: &Services,
+    user_id: Uuid,
+) -> Result<StepUpStatus, AdminError> {
+    let enrollment = services.step_up.get(user_id).await?;
+    let verified_until = match &state.cache {
+        Some(cache) => step_up::verified_until(cache.as_ref(), user_id).await,
+        None => None,
+    };
+    Ok(StepUpStatus {
+        enforced: state.config.auth.step_up.enabled,
+        totp_enrolled: enrollment.as_ref().is_some_and(|e| e.is_verified()),
+        enrollment_pending: enrollment.as_ref().is_some_and(|e| !e.is_verified()),
+        verified_until,
+    })
+}
+
+/// Get step-up status
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/me/step-up",
+    tag = "me",
+    operation_id = "me_step_up_status",
+    responses(
+        (status = 200, description = "Step-up status", body = StepUpStatus),
+        (status = 403, description = "User account required", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.me.step_up.status", skip(state, admin_auth, authz))]
+pub async fn status(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+) -> Result<Json<StepUpStatus>, AdminError> {
+    authz.require("me", "read", None, None, None, None)?;
+    let user_id = get_user_id(&admin_auth)?;
+    let services = get_services(&state)?;
+
+    Ok(Json(build_status(&state, services, user_id).await?))
+}
+
+/// Start TOTP enrollment
+///
+/// Generates a new secret. Restarting a pending enrollment replaces its
+/// secret; a confirmed authenticator must be removed first.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/me/step-up/totp/enroll",
+    tag = "me",
+    operation_id = "me_step_up_totp_enroll",
+    responses(
+        (status = 200, description = "Enrollment started", body = TotpEnrollmentResponse),
+        (status = 403, description = "User account required", body = crate::openapi::ErrorResponse),
+        (status = 409, description = "An authenticator is already enrolled", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.me.step_up.totp_enroll", skip(state, admin_auth, authz))]
+pub async fn enroll(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+) -> Result<Json<TotpEnrollmentResponse>, AdminError> {
+    authz.require("me", "update", None, None, None, None)?;
+    let user_id = get_user_id(&admin_auth)?;
+    let services = get_services(&state)?;
+
+    let secret = services.step_up.begin_enrollment(user_id).await?;
+    let identity = &admin_auth.identity;
+    let account = identity.email.as_deref().unwrap_or(&identity.external_id);
+    let otpauth_uri = totp::otpauth_uri(&state.config.auth.step_up.totp_issuer, account, &secret);
+
+    Ok(Json(TotpEnrollmentResponse {
+        secret,
+        otpauth_uri,
+    }))
+}
+
+/// Confirm TOTP enrollment
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/me/step-up/totp/confirm",
+    tag = "me",
+    operation_id = "me_step_up_totp_confirm",
+    request_body = TotpCodeRequest,
+    responses(
+        (status = 200, description = "Authenticator enrolled", body = StepUpStatus),
+        (status = 400, description = "Invalid code or no pending enrollment", body = crate::openapi::ErrorResponse),
+        (status = 409, description = "Already enrolled", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.me.step_up.totp_confirm", skip_all)]
+pub async fn confirm(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Json(request): Json<TotpCodeRequest>,
+) -> Result<Json<StepUpStatus>, AdminError> {
+    authz.require("me", "update", None, None, None, None)?;
+    let user_id = get_user_id(&admin_auth)?;
+    let services = get_services(&state)?;
+    let actor = AuditActor::from(&admin_auth);
+
+    services
+        .step_up
+        .confirm_enrollment(user_id, &request.code)
+        .await?;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: auth_events::TOTP_ENROLLED.to_string(),
+            resource_type: "user".to_string(),
+            resource_id: user_id,
+            org_id: None,
+            project_id: None,
+            details: json!({ "external_id": admin_auth.identity.external_id }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(Json(build_status(&state, services, user_id).await?))
+}
+
+/// Remove the TOTP authenticator
+///
+/// Protected by step-up itself in the default configuration, so removing an
+/// authenticator needs a fresh sign-in or a current code.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    delete,
+    path = "/admin/v1/me/step-up/totp",
+    tag = "me",
+    operation_id = "me_step_up_totp_delete",
+    responses(
+        (status = 204, description = "Authenticator removed"),
+        (status = 403, description = "Step-up authentication required", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "No authenticator enrolled", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.me.step_up.totp_delete", skip_all)]
+pub async fn remove(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+) -> Result<StatusCode, AdminError> {
+    authz.require("me", "update", None, None, None, None)?;
+    let user_id = get_user_id(&admin_auth)?;
+    let services = get_services(&state)?;
+    let actor = AuditActor::from(&admin_auth);
+
+    if !services.step_up.disable(user_id).await? {
+        return Err(AdminError::NotFound(
+            "No TOTP authenticator enrolled".to_string(),
+        ));
+    }
+    if let Some(cache) = &state.cache {
+        step_up::clear_verified(cache.as_ref(), user_id).await;
+    }
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: auth_events::TOTP_REMOVED.to_string(),
+            resource_type: "user".to_string(),
+            resource_id: user_id,
+            org_id: None,
+            project_id: None,
+            details: json!({ "external_id": admin_auth.identity.external_id }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Verify a TOTP code for step-up
+///
+/// Unlocks protected routes for `verification_ttl_secs`. Repeated failures
+/// lock the user out for the same period.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/me/step-up/verify",
+    tag = "me",
+    operation_id = "me_step_up_verify",
+    request_body = TotpCodeRequest,
+    responses(
+        (status = 200, description = "Code accepted", body = StepUpVerifyResponse),
+        (status = 400, description = "Invalid code or no authenticator enrolled", body = crate::openapi::ErrorResponse),
+        (status = 429, description = "Too many failed attempts", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.me.step_up.verify", skip_all)]
+pub async fn verify(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Json(request): Json<TotpCodeRequest>,
+) -> Result<Json<StepUpVerifyResponse>, AdminError> {
+    authz.require("me", "read", None, None, None, None)?;
+    let user_id = get_user_id(&admin_auth)?;
+    let services = get_services(&state)?;
+
+    let attempt = step_up::attempt_totp(
+        &state,
+        services,
+        &admin_auth.identity,
+        user_id,
+        &request.code,
+        &client_info,
+    )
+    .await?;
+
+    match attempt {
+        TotpAttempt::Verified(verified_until) => Ok(Json(StepUpVerifyResponse { verified_until })),
+        TotpAttempt::Invalid => Err(AdminError::Validation(
+            "Invalid or already used TOTP code".to_string(),
+        )),
+        TotpAttempt::NotEnrolled => Err(AdminError::BadRequest(
+            "No TOTP authenticator is enrolled".to_string(),
+        )),
+        TotpAttempt::LockedOut => Err(AdminError::RateLimited {
+            seconds_remaining: state.config.auth.step_up.verification_ttl_secs,
+            message: "Too many failed step-up attempts; try again later".to_string(),
+        }),
+    }
+}
@@ -0,0 +1,115 @@
+//! Instance lifecycle and background job endpoints.
+//!
+//! The drain endpoints act on the gateway instance that serves the request,
+//! not the whole deployment; call them on each instance directly (e.g. from
+//! a Kubernetes `preStop` hook) rather than through a load balancer. The jobs
+//! endpoint reports cluster-wide state from the database.
+
+use axum::{Extension, Json, extract::State};
+use serde::Serialize;
+
+use super::AdminError;
+use crate::{
+    AppState,
+    jobs::leader_lock,
+    middleware::{AdminAuth, AuthzContext, DrainStatus},
+    models::JobLeader,
+};
+
+/// Get this instance's drain status
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/system/drain",
+    tag = "system",
+    operation_id = "system_drain_status",
+    responses(
+        (status = 200, description = "Drain status", body = DrainStatus),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn drain_status(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+) -> Result<Json<DrainStatus>, AdminError> {
+    authz.require("system", "read", None, None, None, None)?;
+
+    Ok(Json(state.drain.status()))
+}
+
+/// Start draining this instance
+///
+/// The readiness probe starts failing and new data-plane requests are
+/// rejected with 503 and `Retry-After`, while requests already in flight
+/// (including streams) run to completion. Admin and health endpoints keep
+/// working. Draining can't be undone; the instance is expected to be stopped
+/// once `in_flight` reaches zero. Calling this again returns the current
+/// status.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/system/drain",
+    tag = "system",
+    operation_id = "system_drain",
+    responses(
+        (status = 200, description = "Instance is draining", body = DrainStatus),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn drain(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+) -> Result<Json<DrainStatus>, AdminError> {
+    authz.require("system", "drain", None, None, None, None)?;
+
+    if state.drain.start() {
+        tracing::warn!(
+            user_id = ?admin_auth.identity.user_id,
+            external_id = %admin_auth.identity.external_id,
+            "Drain requested through the admin API"
+        );
+    }
+
+    Ok(Json(state.drain.status()))
+}
+
+/// Which node runs each cluster-wide background job.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct SystemJobsResponse {
+    /// The node that served this request
+    pub node_id: String,
+    /// The node that last ran each job, ordered by job name
+    pub jobs: Vec<JobLeader>,
+}
+
+/// List background job leaders
+///
+/// Cleanup-style jobs (retention, DLQ retry, scheduled reports, ...) run on
+/// one node at a time. This lists, for each job, the node that last ran it,
+/// when that node took the job over and when it last ran. A node that stops
+/// is replaced on the next tick by whichever node gets the job's lock first.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/system/jobs",
+    tag = "system",
+    operation_id = "system_jobs",
+    responses(
+        (status = 200, description = "Job leaders", body = SystemJobsResponse),
+        (status = 403, description = "Access denied", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "Database not configured", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn jobs(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+) -> Result<Json<SystemJobsResponse>, AdminError> {
+    authz.require("system", "read", None, None, None, None)?;
+
+    let db = state.db.as_ref().ok_or(AdminError::DatabaseRequired)?;
+    let jobs = db.job_leaders().list().await?;
+
+    Ok(Json(SystemJobsResponse {
+        node_id: leader_lock::node_id().to_string(),
+        jobs,
+    }))
+}
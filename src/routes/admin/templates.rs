@@ -8,11 +8,18 @@ use serde::{Deserialize, Serialize};
 use serde_json::json;
 use uuid::Uuid;
 
-use super::{AuditActor, error::AdminError, organizations::ListQuery};
+use super::{
+    AuditActor,
+    error::AdminError,
+    organizations::{LabelQuery, ListQuery},
+};
 use crate::{
     AppState,
     middleware::{AdminAuth, AuthzContext, ClientInfo},
-    models::{CreateAuditLog, CreateTemplate, Template, TemplateOwnerType, UpdateTemplate},
+    models::{
+        CreateAuditLog, CreateTemplate, SetLabels, Template, TemplateOwnerType, UpdateTemplate,
+        validate_labels,
+    },
     openapi::PaginationMeta,
     services::Services,
 };
@@ -284,6 +291,84 @@ pub async fn update(
     Ok(Json(template))
 }
 
+/// Set a template's labels
+///
+/// Replaces all of the template's labels. Send an empty object to remove
+/// them.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    put,
+    path = "/admin/v1/templates/{id}/labels",
+    tag = "templates",
+    operation_id = "template_set_labels",
+    params(("id" = Uuid, Path, description = "Template ID")),
+    request_body = SetLabels,
+    responses(
+        (status = 200, description = "Labels saved", body = Template),
+        (status = 400, description = "Invalid labels", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Template not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "admin.templates.set_labels", skip(state, admin_auth, authz, input), fields(%id))]
+pub async fn set_labels(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path(id): Path<Uuid>,
+    Json(input): Json<SetLabels>,
+) -> Result<Json<Template>, AdminError> {
+    let services = get_services(&state)?;
+    let actor = AuditActor::from(&admin_auth);
+
+    let existing = services
+        .templates
+        .get_by_id(id)
+        .await?
+        .ok_or_else(|| AdminError::NotFound("Template not found".to_string()))?;
+    let id_str = id.to_string();
+    let scope = template_authz_scope(&existing);
+    authz.require(
+        "template",
+        "update",
+        Some(&id_str),
+        scope.org.as_deref(),
+        scope.team.as_deref(),
+        scope.project.as_deref(),
+    )?;
+
+    validate_labels(&input.labels).map_err(AdminError::Validation)?;
+
+    let template = services.templates.set_labels(id, &input.labels).await?;
+
+    let (org_id, project_id) = match template.owner_type {
+        TemplateOwnerType::Organization => (Some(template.owner_id), None),
+        TemplateOwnerType::Project => (None, Some(template.owner_id)),
+        TemplateOwnerType::Team | TemplateOwnerType::User => (None, None),
+    };
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "template.labels_update".to_string(),
+            resource_type: "template".to_string(),
+            resource_id: template.id,
+            org_id,
+            project_id,
+            details: json!({
+                "name": template.name,
+                "previous": existing.labels,
+                "labels": input.labels,
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(Json(template))
+}
+
 /// Delete a template
 #[cfg_attr(feature = "utoipa", utoipa::path(
     delete,
@@ -372,19 +457,21 @@ pub async fn delete(
     params(
         ("org_slug" = String, Path, description = "Organization slug"),
         ListQuery,
+        LabelQuery,
     ),
     responses(
         (status = 200, description = "List of templates", body = TemplateListResponse),
-        (status = 400, description = "Invalid cursor or direction", body = crate::openapi::ErrorResponse),
+        (status = 400, description = "Invalid cursor, direction or label selector", body = crate::openapi::ErrorResponse),
         (status = 404, description = "Organization not found", body = crate::openapi::ErrorResponse),
     )
 ))]
-#[tracing::instrument(name = "admin.templates.list_by_org", skip(state, authz, query), fields(%org_slug))]
+#[tracing::instrument(name = "admin.templates.list_by_org", skip(state, authz, query, labels), fields(%org_slug))]
 pub async fn list_by_org(
     State(state): State<AppState>,
     Extension(authz): Extension<AuthzContext>,
     Path(org_slug): Path<String>,
     Query(query): Query<ListQuery>,
+    Query(labels): Query<LabelQuery>,
 ) -> Result<Json<TemplateListResponse>, AdminError> {
     let services = get_services(&state)?;
 
@@ -405,7 +492,7 @@ pub async fn list_by_org(
     )?;
 
     let limit = query.limit.unwrap_or(100);
-    let params = query.try_into_with_cursor()?;
+    let params = labels.apply(query.try_into_with_cursor()?)?;
 
     let result = services.templates.list_by_org(org.id, params).await?;
 
@@ -432,19 +519,21 @@ pub async fn list_by_org(
         ("org_slug" = String, Path, description = "Organization slug"),
         ("team_slug" = String, Path, description = "Team slug"),
         ListQuery,
+        LabelQuery,
     ),
     responses(
         (status = 200, description = "List of templates", body = TemplateListResponse),
-        (status = 400, description = "Invalid cursor or direction", body = crate::openapi::ErrorResponse),
+        (status = 400, description = "Invalid cursor, direction or label selector", body = crate::openapi::ErrorResponse),
         (status = 404, description = "Organization or team not found", body = crate::openapi::ErrorResponse),
     )
 ))]
-#[tracing::instrument(name = "admin.templates.list_by_team", skip(state, authz, query), fields(%org_slug, %team_slug))]
+#[tracing::instrument(name = "admin.templates.list_by_team", skip(state, authz, query, labels), fields(%org_slug, %team_slug))]
 pub async fn list_by_team(
     State(state): State<AppState>,
     Extension(authz): Extension<AuthzContext>,
     Path((org_slug, team_slug)): Path<(String, String)>,
     Query(query): Query<ListQuery>,
+    Query(labels): Query<LabelQuery>,
 ) -> Result<Json<TemplateListResponse>, AdminError> {
     let services = get_services(&state)?;
 
@@ -477,7 +566,7 @@ pub async fn list_by_team(
     )?;
 
     let limit = query.limit.unwrap_or(100);
-    let params = query.try_into_with_cursor()?;
+    let params = labels.apply(query.try_into_with_cursor()?)?;
 
     let result = services
         .templates
@@ -507,19 +596,21 @@ pub async fn list_by_team(
         ("org_slug" = String, Path, description = "Organization slug"),
         ("project_slug" = String, Path, description = "Project slug"),
         ListQuery,
+        LabelQuery,
     ),
     responses(
         (status = 200, description = "List of templates", body = TemplateListResponse),
-        (status = 400, description = "Invalid cursor or direction", body = crate::openapi::ErrorResponse),
+        (status = 400, description = "Invalid cursor, direction or label selector", body = crate::openapi::ErrorResponse),
         (status = 404, description = "Organization or project not found", body = crate::openapi::ErrorResponse),
     )
 ))]
-#[tracing::instrument(name = "admin.templates.list_by_project", skip(state, authz, query), fields(%org_slug, %project_slug))]
+#[tracing::instrument(name = "admin.templates.list_by_project", skip(state, authz, query, labels), fields(%org_slug, %project_slug))]
 pub async fn list_by_project(
     State(state): State<AppState>,
     Extension(authz): Extension<AuthzContext>,
     Path((org_slug, project_slug)): Path<(String, String)>,
     Query(query): Query<ListQuery>,
+    Query(labels): Query<LabelQuery>,
 ) -> Result<Json<TemplateListResponse>, AdminError> {
     let services = get_services(&state)?;
 
@@ -552,7 +643,7 @@ pub async fn list_by_project(
     )?;
 
     let limit = query.limit.unwrap_or(100);
-    let params = query.try_into_with_cursor()?;
+    let params = labels.apply(query.try_into_with_cursor()?)?;
 
     let result = services
         .templates
@@ -581,18 +672,20 @@ pub async fn list_by_project(
     params(
         ("user_id" = Uuid, Path, description = "User ID"),
         ListQuery,
+        LabelQuery,
     ),
     responses(
         (status = 200, description = "List of templates", body = TemplateListResponse),
-        (status = 400, description = "Invalid cursor or direction", body = crate::openapi::ErrorResponse),
+        (status = 400, description = "Invalid cursor, direction or label selector", body = crate::openapi::ErrorResponse),
     )
 ))]
-#[tracing::instrument(name = "admin.templates.list_by_user", skip(state, authz, query), fields(%user_id))]
+#[tracing::instrument(name = "admin.templates.list_by_user", skip(state, authz, query, labels), fields(%user_id))]
 pub async fn list_by_user(
     State(state): State<AppState>,
     Extension(authz): Extension<AuthzContext>,
     Path(user_id): Path<Uuid>,
     Query(query): Query<ListQuery>,
+    Query(labels): Query<LabelQuery>,
 ) -> Result<Json<TemplateListResponse>, AdminError> {
     let services = get_services(&state)?;
 
@@ -602,7 +695,7 @@ pub async fn list_by_user(
     authz.require("template", "list", Some(&user_id_str), None, None, None)?;
 
     let limit = query.limit.unwrap_or(100);
-    let params = query.try_into_with_cursor()?;
+    let params = labels.apply(query.try_into_with_cursor()?)?;
 
     let result = services
         .templates
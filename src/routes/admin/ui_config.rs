@@ -1,5 +1,8 @@
-use axum::{Json, extract::State};
-use serde::Serialize;
+use axum::{
+    Json,
+    extract::{Query, State},
+};
+use serde::{Deserialize, Serialize};
 
 use crate::{
     AppState,
@@ -8,6 +11,7 @@ use crate::{
         CustomFont, FavoriteMcpServer, FontsConfig, LoginConfig, McpUiConfig, PageConfig,
         PageStatus, PagesConfig, UiConfig,
     },
+    models::RequestDefaults,
 };
 
 /// UI configuration response for frontend applications.
@@ -161,6 +165,30 @@ pub struct ChatResponse {
     pub file_uploads_enabled: bool,
     pub max_file_size_bytes: usize,
     pub allowed_file_types: Vec<String>,
+    /// Org/project request defaults, present when `?org=` (and optionally
+    /// `&project=`) is given and defaults are configured.
+    pub presets: Option<ChatPresetsResponse>,
+}
+
+/// Request defaults the gateway applies for an org/project, so the chat UI
+/// can preselect them.
+///
+/// The system prompt text is not included because this endpoint is served
+/// before login; the UI only learns whether one will be injected.
+#[derive(Debug, Serialize)]
+pub struct ChatPresetsResponse {
+    pub default_model: Option<String>,
+    pub temperature: Option<f64>,
+    pub has_system_prompt: bool,
+}
+
+/// Optional scope for resolving chat presets.
+#[derive(Debug, Default, Deserialize)]
+pub struct UiConfigQuery {
+    /// Organization slug
+    pub org: Option<String>,
+    /// Project slug within `org`
+    pub project: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -380,6 +408,7 @@ impl From<&ChatConfig> for ChatResponse {
             file_uploads_enabled: config.file_uploads.enabled,
             max_file_size_bytes: config.file_uploads.max_size_bytes,
             allowed_file_types: config.file_uploads.allowed_types.clone(),
+            presets: None,
         }
     }
 }
@@ -403,10 +432,15 @@ impl Default for AuthResponse {
 
 /// Get UI configuration for frontend applications.
 /// This endpoint is unauthenticated so the UI can fetch it before login.
-pub async fn get_ui_config(State(state): State<AppState>) -> Json<UiConfigResponse> {
+pub async fn get_ui_config(
+    State(state): State<AppState>,
+    Query(query): Query<UiConfigQuery>,
+) -> Json<UiConfigResponse> {
     let ui_config = &state.config.ui;
     let mut response = UiConfigResponse::from(ui_config);
 
+    response.chat.presets = resolve_chat_presets(&state, &query).await;
+
     // With [features.containers] disabled the shell tool never persists
     // containers, so the Containers page would only ever show an empty
     // list — hide it regardless of [ui.pages] settings.
@@ -475,3 +509,35 @@ pub async fn get_ui_config(State(state): State<AppState>) -> Json<UiConfigRespon
 
     Json(response)
 }
+
+/// Look up the merged org/project request defaults for the chat UI.
+///
+/// Unknown slugs and lookup errors yield no presets rather than an error, so
+/// the rest of the UI config is always served.
+async fn resolve_chat_presets(
+    state: &AppState,
+    query: &UiConfigQuery,
+) -> Option<ChatPresetsResponse> {
+    let services = state.services.as_ref()?;
+    let org = services
+        .organizations
+        .get_by_slug(query.org.as_deref()?)
+        .await
+        .ok()??;
+    let project = match query.project.as_deref() {
+        Some(slug) => services.projects.get_by_slug(org.id, slug).await.ok()?,
+        None => None,
+    };
+
+    let defaults = RequestDefaults::merge(
+        org.request_defaults.as_ref(),
+        project.and_then(|p| p.request_defaults).as_ref(),
+    )
+    .filter(|d| !d.is_empty())?;
+
+    Some(ChatPresetsResponse {
+        default_model: defaults.model,
+        temperature: defaults.temperature,
+        has_system_prompt: defaults.system_prompt.is_some(),
+    })
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use axum::{
     Extension, Json,
     extract::{Path, Query, State},
@@ -14,12 +16,17 @@ use crate::{
     middleware::{AdminAuth, AuthzContext},
     models::{
         CostForecast, DailyModelSpend, DailyOrgSpend, DailyPricingSourceSpend, DailyProjectSpend,
-        DailyProviderSpend, DailySpend, DailyTeamSpend, DailyUserSpend, ModelSpend, OrgSpend,
-        PricingSourceSpend, ProjectSpend, ProviderSpend, RefererSpend, TeamSpend, UsageLogRecord,
-        UsageSummary, UserSpend,
+        DailyProviderSpend, DailySpend, DailyTagSpend, DailyTeamSpend, DailyUserSpend, ModelSpend,
+        OrgSpend, PricingSourceSpend, ProjectSpend, ProviderSpend, RefererSpend, TagSpend,
+        TeamSpend, UsageLogRecord, UsageSummary, UserSpend,
     },
     openapi::PaginationMeta,
-    services::Services,
+    services::{
+        Services,
+        usage_reconciliation::{self, ProviderReconciliation},
+        usage_reliability::{self, ReliabilityDimension, ReliabilityRow, ReliabilityStats},
+        usage_tags,
+    },
 };
 
 /// Query parameters for usage endpoints
@@ -3911,6 +3918,521 @@ pub async fn get_global_by_date_org(
     Ok(Json(data.into_iter().map(|s| s.into()).collect()))
 }
 
+// ==================== Reconciliation ====================
+
+/// Gateway usage compared with provider-reported usage
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct UsageReconciliationResponse {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    /// One entry per provider with usage or a configured usage API
+    pub providers: Vec<ProviderReconciliation>,
+}
+
+/// Compare gateway usage with provider-reported usage
+///
+/// Daily request and token counts per provider and model, with the attempts
+/// sent upstream and attempts that timed out before a retry (which the
+/// provider may have billed). Provider counts come from the usage APIs in
+/// `[features.usage_reconciliation]`.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/usage/reconciliation",
+    tag = "usage",
+    operation_id = "usage_get_reconciliation",
+    params(UsageQuery),
+    responses(
+        (status = 200, description = "Usage reconciliation report", body = UsageReconciliationResponse),
+    )
+))]
+pub async fn get_reconciliation(
+    State(state): State<AppState>,
+    Query(query): Query<UsageQuery>,
+    Extension(authz): Extension<AuthzContext>,
+) -> Result<Json<UsageReconciliationResponse>, AdminError> {
+    authz.require("usage", "read", None, None, None, None)?;
+    let services = get_services(&state)?;
+    let range = query.parse_date_range()?;
+    let gateway = services
+        .usage
+        .get_daily_provider_attempts(range.clone())
+        .await?;
+    let providers = usage_reconciliation::reconcile(
+        &state.http_client,
+        &state.config.features.usage_reconciliation,
+        gateway,
+        &range,
+    )
+    .await;
+    Ok(Json(UsageReconciliationResponse {
+        start_date: range.start,
+        end_date: range.end,
+        providers,
+    }))
+}
+
+// ==================== Cost Allocation Tags ====================
+
+/// Query parameters for tag usage endpoints
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema, utoipa::IntoParams))]
+pub struct TagUsageQuery {
+    /// Tag key to group by, e.g. `feature`
+    pub key: String,
+    /// Start date (YYYY-MM-DD)
+    pub start_date: Option<String>,
+    /// End date (YYYY-MM-DD)
+    pub end_date: Option<String>,
+}
+
+impl TagUsageQuery {
+    fn parse(&self) -> Result<(String, DateRange), AdminError> {
+        let key = self.key.trim().to_ascii_lowercase();
+        if !usage_tags::is_valid_key(&key) {
+            return Err(AdminError::BadRequest(format!(
+                "Invalid tag key '{}': use up to {} lowercase letters, digits, '_', '-' or '.'",
+                self.key,
+                usage_tags::MAX_KEY_LENGTH
+            )));
+        }
+        let range = UsageQuery {
+            start_date: self.start_date.clone(),
+            end_date: self.end_date.clone(),
+        }
+        .parse_date_range()?;
+        Ok((key, range))
+    }
+}
+
+/// Usage breakdown by the value of a cost allocation tag
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct TagSpendResponse {
+    /// Tag value; `null` for requests without the tag
+    pub tag_value: Option<String>,
+    /// Total cost in dollars for this tag value
+    pub total_cost: f64,
+    /// Input tokens used
+    pub input_tokens: i64,
+    /// Output tokens used
+    pub output_tokens: i64,
+    /// Total tokens used
+    pub total_tokens: i64,
+    /// Number of requests
+    pub request_count: i64,
+}
+
+impl From<TagSpend> for TagSpendResponse {
+    fn from(spend: TagSpend) -> Self {
+        Self {
+            tag_value: spend.tag_value,
+            total_cost: spend.total_cost_microcents as f64 / 1_000_000.0,
+            input_tokens: spend.input_tokens,
+            output_tokens: spend.output_tokens,
+            total_tokens: spend.total_tokens,
+            request_count: spend.request_count,
+        }
+    }
+}
+
+/// Daily usage breakdown by the value of a cost allocation tag
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct DailyTagSpendResponse {
+    /// Date (YYYY-MM-DD)
+    pub date: String,
+    /// Tag value; `null` for requests without the tag
+    pub tag_value: Option<String>,
+    /// Total cost in dollars
+    pub total_cost: f64,
+    /// Input tokens used
+    pub input_tokens: i64,
+    /// Output tokens used
+    pub output_tokens: i64,
+    /// Total tokens used
+    pub total_tokens: i64,
+    /// Number of requests
+    pub request_count: i64,
+}
+
+impl From<DailyTagSpend> for DailyTagSpendResponse {
+    fn from(spend: DailyTagSpend) -> Self {
+        Self {
+            date: spend.date.to_string(),
+            tag_value: spend.tag_value,
+            total_cost: spend.total_cost_microcents as f64 / 1_000_000.0,
+            input_tokens: spend.input_tokens,
+            output_tokens: spend.output_tokens,
+            total_tokens: spend.total_tokens,
+            request_count: spend.request_count,
+        }
+    }
+}
+
+/// Resolve an organization slug and authorize reading its usage.
+async fn authorize_org_usage(
+    services: &Services,
+    authz: &AuthzContext,
+    slug: &str,
+) -> Result<Uuid, AdminError> {
+    let org = services
+        .organizations
+        .get_by_slug(slug)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("Organization not found: {slug}")))?;
+    authz.require("usage", "read", None, Some(&org.id.to_string()), None, None)?;
+    Ok(org.id)
+}
+
+/// Resolve organization and project slugs and authorize reading the
+/// project's usage.
+async fn authorize_project_usage(
+    services: &Services,
+    authz: &AuthzContext,
+    path: &ProjectUsagePath,
+) -> Result<(Uuid, Uuid), AdminError> {
+    let org_id = authorize_org_usage(services, authz, &path.org_slug).await?;
+    let project = services
+        .projects
+        .get_by_slug(org_id, &path.project_slug)
+        .await?
+        .ok_or_else(|| {
+            AdminError::NotFound(format!(
+                "Project not found: {}/{}",
+                path.org_slug, path.project_slug
+            ))
+        })?;
+    Ok((org_id, project.id))
+}
+
+/// Get usage by tag value across all organizations
+///
+/// Groups spend by the value clients sent for tag `key` in the
+/// `X-Hadrian-Tags` header. Requests without the tag are grouped under a
+/// `null` value.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/usage/by-tag",
+    tag = "usage",
+    operation_id = "usage_get_global_by_tag",
+    params(TagUsageQuery),
+    responses(
+        (status = 200, description = "Usage breakdown by tag value", body = Vec<TagSpendResponse>),
+        (status = 400, description = "Invalid tag key", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn get_global_by_tag(
+    State(state): State<AppState>,
+    Query(query): Query<TagUsageQuery>,
+    Extension(authz): Extension<AuthzContext>,
+) -> Result<Json<Vec<TagSpendResponse>>, AdminError> {
+    authz.require("usage", "read", None, None, None, None)?;
+    let services = get_services(&state)?;
+    let (key, range) = query.parse()?;
+    let data = services.usage.get_by_tag(&key, range, None, None).await?;
+    Ok(Json(data.into_iter().map(|s| s.into()).collect()))
+}
+
+/// Get daily usage by tag value across all organizations
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/usage/by-date-tag",
+    tag = "usage",
+    operation_id = "usage_get_global_by_date_tag",
+    params(TagUsageQuery),
+    responses(
+        (status = 200, description = "Daily usage breakdown by tag value", body = Vec<DailyTagSpendResponse>),
+        (status = 400, description = "Invalid tag key", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn get_global_by_date_tag(
+    State(state): State<AppState>,
+    Query(query): Query<TagUsageQuery>,
+    Extension(authz): Extension<AuthzContext>,
+) -> Result<Json<Vec<DailyTagSpendResponse>>, AdminError> {
+    authz.require("usage", "read", None, None, None, None)?;
+    let services = get_services(&state)?;
+    let (key, range) = query.parse()?;
+    let data = services
+        .usage
+        .get_by_date_tag(&key, range, None, None)
+        .await?;
+    Ok(Json(data.into_iter().map(|s| s.into()).collect()))
+}
+
+/// Get usage by tag value for an organization
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/organizations/{slug}/usage/by-tag",
+    tag = "usage",
+    operation_id = "usage_get_org_by_tag",
+    params(
+        ("slug" = String, Path, description = "Organization slug"),
+        TagUsageQuery,
+    ),
+    responses(
+        (status = 200, description = "Usage breakdown by tag value", body = Vec<TagSpendResponse>),
+        (status = 400, description = "Invalid tag key", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn get_org_by_tag(
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    Query(query): Query<TagUsageQuery>,
+    Extension(authz): Extension<AuthzContext>,
+) -> Result<Json<Vec<TagSpendResponse>>, AdminError> {
+    let services = get_services(&state)?;
+    let org_id = authorize_org_usage(services, &authz, &slug).await?;
+    let (key, range) = query.parse()?;
+    let data = services
+        .usage
+        .get_by_tag(&key, range, Some(org_id), None)
+        .await?;
+    Ok(Json(data.into_iter().map(|s| s.into()).collect()))
+}
+
+/// Get daily usage by tag value for an organization
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/organizations/{slug}/usage/by-date-tag",
+    tag = "usage",
+    operation_id = "usage_get_org_by_date_tag",
+    params(
+        ("slug" = String, Path, description = "Organization slug"),
+        TagUsageQuery,
+    ),
+    responses(
+        (status = 200, description = "Daily usage breakdown by tag value", body = Vec<DailyTagSpendResponse>),
+        (status = 400, description = "Invalid tag key", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Organization not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn get_org_by_date_tag(
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    Query(query): Query<TagUsageQuery>,
+    Extension(authz): Extension<AuthzContext>,
+) -> Result<Json<Vec<DailyTagSpendResponse>>, AdminError> {
+    let services = get_services(&state)?;
+    let org_id = authorize_org_usage(services, &authz, &slug).await?;
+    let (key, range) = query.parse()?;
+    let data = services
+        .usage
+        .get_by_date_tag(&key, range, Some(org_id), None)
+        .await?;
+    Ok(Json(data.into_iter().map(|s| s.into()).collect()))
+}
+
+/// Get usage by tag value for a project
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/organizations/{org_slug}/projects/{project_slug}/usage/by-tag",
+    tag = "usage",
+    operation_id = "usage_get_project_by_tag",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("project_slug" = String, Path, description = "Project slug"),
+        TagUsageQuery,
+    ),
+    responses(
+        (status = 200, description = "Usage breakdown by tag value", body = Vec<TagSpendResponse>),
+        (status = 400, description = "Invalid tag key", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Project not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn get_project_by_tag(
+    State(state): State<AppState>,
+    Path(path): Path<ProjectUsagePath>,
+    Query(query): Query<TagUsageQuery>,
+    Extension(authz): Extension<AuthzContext>,
+) -> Result<Json<Vec<TagSpendResponse>>, AdminError> {
+    let services = get_services(&state)?;
+    let (org_id, project_id) = authorize_project_usage(services, &authz, &path).await?;
+    let (key, range) = query.parse()?;
+    let data = services
+        .usage
+        .get_by_tag(&key, range, Some(org_id), Some(project_id))
+        .await?;
+    Ok(Json(data.into_iter().map(|s| s.into()).collect()))
+}
+
+/// Get daily usage by tag value for a project
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/organizations/{org_slug}/projects/{project_slug}/usage/by-date-tag",
+    tag = "usage",
+    operation_id = "usage_get_project_by_date_tag",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("project_slug" = String, Path, description = "Project slug"),
+        TagUsageQuery,
+    ),
+    responses(
+        (status = 200, description = "Daily usage breakdown by tag value", body = Vec<DailyTagSpendResponse>),
+        (status = 400, description = "Invalid tag key", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Project not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn get_project_by_date_tag(
+    State(state): State<AppState>,
+    Path(path): Path<ProjectUsagePath>,
+    Query(query): Query<TagUsageQuery>,
+    Extension(authz): Extension<AuthzContext>,
+) -> Result<Json<Vec<DailyTagSpendResponse>>, AdminError> {
+    let services = get_services(&state)?;
+    let (org_id, project_id) = authorize_project_usage(services, &authz, &path).await?;
+    let (key, range) = query.parse()?;
+    let data = services
+        .usage
+        .get_by_date_tag(&key, range, Some(org_id), Some(project_id))
+        .await?;
+    Ok(Json(data.into_iter().map(|s| s.into()).collect()))
+}
+
+// ==================== Reliability ====================
+
+/// Query parameters for reliability endpoints
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema, utoipa::IntoParams))]
+pub struct ReliabilityQuery {
+    /// Start date (YYYY-MM-DD)
+    pub start_date: Option<String>,
+    /// End date (YYYY-MM-DD)
+    pub end_date: Option<String>,
+    /// Dimension to break statistics down by (default: `date`)
+    #[serde(default)]
+    pub group_by: ReliabilityDimension,
+}
+
+impl ReliabilityQuery {
+    fn parse_date_range(&self) -> Result<DateRange, AdminError> {
+        UsageQuery {
+            start_date: self.start_date.clone(),
+            end_date: self.end_date.clone(),
+        }
+        .parse_date_range()
+    }
+}
+
+/// Latency, error and cache statistics for model requests
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct UsageReliabilityResponse {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub group_by: ReliabilityDimension,
+    /// Statistics across the whole range
+    pub totals: ReliabilityStats,
+    /// Statistics per value of `group_by`
+    pub breakdown: Vec<ReliabilityRow>,
+}
+
+async fn reliability_response(
+    services: &Services,
+    query: &ReliabilityQuery,
+    org_id: Option<Uuid>,
+    project_id: Option<Uuid>,
+) -> Result<UsageReliabilityResponse, AdminError> {
+    let range = query.parse_date_range()?;
+    let rows = services
+        .usage
+        .get_daily_reliability(range.clone(), org_id, project_id)
+        .await?;
+    Ok(UsageReliabilityResponse {
+        start_date: range.start,
+        end_date: range.end,
+        group_by: query.group_by,
+        totals: usage_reliability::totals(&rows),
+        breakdown: usage_reliability::breakdown(&rows, query.group_by),
+    })
+}
+
+/// Get reliability statistics across all organizations
+///
+/// Latency percentiles (p50/p95/p99), error counts by class (provider errors,
+/// guardrail blocks, rate limits) and prompt cache hit rates for model
+/// requests, broken down by date, model, provider or project.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/usage/reliability",
+    tag = "usage",
+    operation_id = "usage_get_global_reliability",
+    params(ReliabilityQuery),
+    responses(
+        (status = 200, description = "Reliability statistics", body = UsageReliabilityResponse),
+    )
+))]
+pub async fn get_global_reliability(
+    State(state): State<AppState>,
+    Query(query): Query<ReliabilityQuery>,
+    Extension(authz): Extension<AuthzContext>,
+) -> Result<Json<UsageReliabilityResponse>, AdminError> {
+    authz.require("usage", "read", None, None, None, None)?;
+    let services = get_services(&state)?;
+    Ok(Json(
+        reliability_response(services, &query, None, None).await?,
+    ))
+}
+
+/// Get reliability statistics for an organization
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/organizations/{slug}/usage/reliability",
+    tag = "usage",
+    operation_id = "usage_get_org_reliability",
+    params(
+        ("slug" = String, Path, description = "Organization slug"),
+        ReliabilityQuery,
+    ),
+    responses(
+        (status = 200, description = "Reliability statistics", body = UsageReliabilityResponse),
+        (status = 404, description = "Organization not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn get_org_reliability(
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    Query(query): Query<ReliabilityQuery>,
+    Extension(authz): Extension<AuthzContext>,
+) -> Result<Json<UsageReliabilityResponse>, AdminError> {
+    let services = get_services(&state)?;
+    let org_id = authorize_org_usage(services, &authz, &slug).await?;
+    Ok(Json(
+        reliability_response(services, &query, Some(org_id), None).await?,
+    ))
+}
+
+/// Get reliability statistics for a project
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/organizations/{org_slug}/projects/{project_slug}/usage/reliability",
+    tag = "usage",
+    operation_id = "usage_get_project_reliability",
+    params(
+        ("org_slug" = String, Path, description = "Organization slug"),
+        ("project_slug" = String, Path, description = "Project slug"),
+        ReliabilityQuery,
+    ),
+    responses(
+        (status = 200, description = "Reliability statistics", body = UsageReliabilityResponse),
+        (status = 404, description = "Project not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn get_project_reliability(
+    State(state): State<AppState>,
+    Path(path): Path<ProjectUsagePath>,
+    Query(query): Query<ReliabilityQuery>,
+    Extension(authz): Extension<AuthzContext>,
+) -> Result<Json<UsageReliabilityResponse>, AdminError> {
+    let services = get_services(&state)?;
+    let (org_id, project_id) = authorize_project_usage(services, &authz, &path).await?;
+    Ok(Json(
+        reliability_response(services, &query, Some(org_id), Some(project_id)).await?,
+    ))
+}
+
 // ==================== Usage Log Endpoints ====================
 
 /// Query parameters for usage log list endpoints
@@ -3926,6 +4448,8 @@ pub struct UsageLogQueryParams {
     pub model: Option<String>,
     pub provider: Option<String>,
     pub provider_source: Option<String>,
+    /// Filter by error category (e.g. `rate_limited`, `context_length`)
+    pub error_code: Option<String>,
     pub from: Option<DateTime<Utc>>,
     pub to: Option<DateTime<Utc>>,
     pub limit: Option<i64>,
@@ -3951,6 +4475,7 @@ impl UsageLogQueryParams {
             cursor: self.cursor,
             direction: self.direction,
             record_type: None,
+            error_code: self.error_code,
         }
     }
 
@@ -3971,6 +4496,7 @@ impl UsageLogQueryParams {
             cursor: None,
             direction: None,
             record_type: None,
+            error_code: self.error_code,
         }
     }
 }
@@ -4007,6 +4533,32 @@ pub struct UsageLogResponse {
     pub audio_seconds: Option<i32>,
     pub character_count: Option<i32>,
     pub provider_source: Option<String>,
+    /// Error category for failed requests (e.g. `rate_limited`)
+    pub error_code: Option<String>,
+    /// Repairs made to a `json_schema` response, when it was validated
+    pub structured_output_repairs: Option<i32>,
+    /// Estimated prompt tokens as sent, when context compression ran
+    pub context_original_tokens: Option<i32>,
+    /// Estimated prompt tokens after context compression
+    pub context_compressed_tokens: Option<i32>,
+    /// Model the client requested, when the request was downgraded
+    pub degraded_from_model: Option<String>,
+    /// Why the request was downgraded
+    pub degradation_reason: Option<String>,
+    /// Time to first token in milliseconds, for streamed requests
+    pub ttft_ms: Option<i32>,
+    /// Mean gap between streamed chunks in milliseconds
+    pub inter_token_latency_ms: Option<f64>,
+    /// Output tokens per second of generation, for streamed requests
+    pub output_tokens_per_second: Option<f64>,
+    /// `Idempotency-Key` the client sent with the request
+    pub idempotency_key: Option<String>,
+    /// Attempts sent to the provider, counting retries
+    pub provider_attempts: Option<i32>,
+    /// Earlier attempts that timed out and may still have been billed upstream
+    pub suspected_duplicate_attempts: Option<i32>,
+    /// Cost allocation tags the client sent in `X-Hadrian-Tags`
+    pub tags: Option<BTreeMap<String, String>>,
 }
 
 impl From<UsageLogRecord> for UsageLogResponse {
@@ -4039,6 +4591,19 @@ impl From<UsageLogRecord> for UsageLogResponse {
             audio_seconds: r.audio_seconds,
             character_count: r.character_count,
             provider_source: r.provider_source,
+            error_code: r.error_code,
+            structured_output_repairs: r.structured_output_repairs,
+            context_original_tokens: r.context_original_tokens,
+            context_compressed_tokens: r.context_compressed_tokens,
+            degraded_from_model: r.degraded_from_model,
+            degradation_reason: r.degradation_reason,
+            ttft_ms: r.ttft_ms,
+            inter_token_latency_ms: r.inter_token_latency_ms,
+            output_tokens_per_second: r.output_tokens_per_second,
+            idempotency_key: r.idempotency_key,
+            provider_attempts: r.provider_attempts,
+            suspected_duplicate_attempts: r.suspected_duplicate_attempts,
+            tags: r.tags,
         }
     }
 }
@@ -4074,6 +4639,8 @@ pub struct UsageLogExportQuery {
     pub model: Option<String>,
     pub provider: Option<String>,
     pub provider_source: Option<String>,
+    /// Filter by error category (e.g. `rate_limited`, `context_length`)
+    pub error_code: Option<String>,
     pub from: Option<DateTime<Utc>>,
     pub to: Option<DateTime<Utc>>,
     #[serde(default)]
@@ -4094,6 +4661,7 @@ impl UsageLogExportQuery {
                 model: self.model,
                 provider: self.provider,
                 provider_source: self.provider_source,
+                error_code: self.error_code,
                 from: self.from,
                 to: self.to,
                 limit: None,
@@ -4227,6 +4795,7 @@ struct UsageLogCsvRow {
     latency_ms: String,
     cancelled: bool,
     status_code: String,
+    error_code: String,
     user_id: String,
     api_key_id: String,
     org_id: String,
@@ -4234,6 +4803,8 @@ struct UsageLogCsvRow {
     team_id: String,
     service_account_id: String,
     pricing_source: String,
+    /// `key=value` pairs separated by `;`
+    tags: String,
 }
 
 fn build_export_response(
@@ -4265,6 +4836,7 @@ fn build_export_response(
                     latency_ms: resp.latency_ms.map(|v| v.to_string()).unwrap_or_default(),
                     cancelled: resp.cancelled,
                     status_code: resp.status_code.map(|v| v.to_string()).unwrap_or_default(),
+                    error_code: resp.error_code.unwrap_or_default(),
                     user_id: resp.user_id.map(|v| v.to_string()).unwrap_or_default(),
                     api_key_id: resp.api_key_id.map(|v| v.to_string()).unwrap_or_default(),
                     org_id: resp.org_id.map(|v| v.to_string()).unwrap_or_default(),
@@ -4275,6 +4847,13 @@ fn build_export_response(
                         .map(|v| v.to_string())
                         .unwrap_or_default(),
                     pricing_source: resp.pricing_source,
+                    tags: resp
+                        .tags
+                        .unwrap_or_default()
+                        .iter()
+                        .map(|(k, v)| format!("{k}={v}"))
+                        .collect::<Vec<_>>()
+                        .join(";"),
                 };
                 wtr.serialize(&row)
                     .map_err(|e| AdminError::Internal(format!("CSV serialization error: {}", e)))?;
@@ -0,0 +1,294 @@
+//! Admin API endpoints for usage anomalies.
+//!
+//! Findings are recorded by the anomaly detection job configured in
+//! `[features.anomaly_detection]`; see [`crate::services::usage_anomalies`]
+//! for how usage is compared with the baseline.
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+use super::{AuditActor, error::AdminError};
+use crate::{
+    AppState,
+    db::{Cursor, CursorDirection, ListParams},
+    middleware::{AdminAuth, AuthzContext, ClientInfo},
+    models::{AnomalyKind, CreateAuditLog, UsageAnomaly, UsageAnomalyFilter},
+    openapi::PaginationMeta,
+    services::UsageAnomalyService,
+};
+
+/// Query parameters for listing usage anomalies.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema, utoipa::IntoParams))]
+pub struct UsageAnomalyListQuery {
+    /// Filter by organization ID.
+    pub org_id: Option<Uuid>,
+    /// Filter by API key ID.
+    pub api_key_id: Option<Uuid>,
+    /// Filter by kind of anomaly.
+    pub kind: Option<AnomalyKind>,
+    /// Only anomalies that haven't been acknowledged.
+    #[serde(default)]
+    pub unacknowledged: bool,
+    /// Maximum number of results to return (default: 100).
+    pub limit: Option<i64>,
+    /// Cursor for keyset pagination. Encoded as base64 string.
+    #[cfg_attr(
+        feature = "utoipa",
+        schema(example = "MTczMzU4MDgwMDAwMDphYmMxMjM0NS02Nzg5LTAxMjMtNDU2Ny0wMTIzNDU2Nzg5YWI")
+    )]
+    pub cursor: Option<String>,
+    /// Pagination direction: "forward" (default) or "backward".
+    #[serde(default)]
+    pub direction: Option<String>,
+}
+
+impl UsageAnomalyListQuery {
+    /// Split into repository filter and list params, rejecting invalid cursors.
+    fn try_into_parts(self) -> Result<(UsageAnomalyFilter, ListParams), AdminError> {
+        let cursor = match &self.cursor {
+            Some(c) => Some(
+                Cursor::decode(c)
+                    .map_err(|e| AdminError::BadRequest(format!("Invalid cursor: {}", e)))?,
+            ),
+            None => None,
+        };
+
+        let direction = match self.direction.as_deref() {
+            Some("backward") => CursorDirection::Backward,
+            Some("forward") | None => CursorDirection::Forward,
+            Some(other) => {
+                return Err(AdminError::BadRequest(format!(
+                    "Invalid direction '{}': must be 'forward' or 'backward'",
+                    other
+                )));
+            }
+        };
+
+        let filter = UsageAnomalyFilter {
+            org_id: self.org_id,
+            api_key_id: self.api_key_id,
+            kind: self.kind,
+            unacknowledged: self.unacknowledged,
+        };
+        let params = ListParams {
+            limit: Some(self.limit.unwrap_or(100)),
+            cursor,
+            direction,
+            ..Default::default()
+        }
+        .clamp();
+        Ok((filter, params))
+    }
+}
+
+/// Paginated list of usage anomalies
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct UsageAnomalyListResponse {
+    /// Usage anomalies, newest first
+    pub data: Vec<UsageAnomaly>,
+    /// Pagination metadata
+    pub pagination: PaginationMeta,
+}
+
+fn get_service(state: &AppState) -> Result<&UsageAnomalyService, AdminError> {
+    state
+        .services
+        .as_ref()
+        .map(|s| &s.usage_anomalies)
+        .ok_or(AdminError::ServicesRequired)
+}
+
+/// Pin `filter` to the caller's organization when they are an org member.
+fn scope_to_membership(
+    authz: &AuthzContext,
+    filter: &mut UsageAnomalyFilter,
+) -> Result<(), AdminError> {
+    let Some(membership) = authz.subject.org_ids.first() else {
+        return Ok(());
+    };
+    let scoped: Uuid = membership.parse().map_err(|_| {
+        AdminError::Internal(
+            "usage_anomaly:list authz subject has a non-UUID org membership".to_string(),
+        )
+    })?;
+    match filter.org_id {
+        Some(requested) if requested != scoped => Err(AdminError::Forbidden(
+            "usage_anomaly:list scoped outside your organization".to_string(),
+        )),
+        _ => {
+            filter.org_id = Some(scoped);
+            Ok(())
+        }
+    }
+}
+
+/// List usage anomalies
+///
+/// Returns spend spikes, model mix shifts and new referers found by anomaly
+/// detection, newest first.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/anomalies",
+    tag = "usage",
+    operation_id = "usage_anomaly_list",
+    params(UsageAnomalyListQuery),
+    responses(
+        (status = 200, description = "List of usage anomalies", body = UsageAnomalyListResponse),
+        (status = 400, description = "Invalid cursor or direction", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Organization outside caller's scope", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn list(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Query(query): Query<UsageAnomalyListQuery>,
+) -> Result<Json<UsageAnomalyListResponse>, AdminError> {
+    let service = get_service(&state)?;
+    let (mut filter, params) = query.try_into_parts()?;
+    let limit = params.limit.unwrap_or(100);
+
+    scope_to_membership(&authz, &mut filter)?;
+    let org_scope = filter.org_id.map(|id| id.to_string());
+    authz.require(
+        "usage_anomaly",
+        "list",
+        None,
+        org_scope.as_deref(),
+        None,
+        None,
+    )?;
+
+    let result = service.list(&filter, params).await?;
+
+    let pagination = PaginationMeta::with_cursors(
+        limit,
+        result.has_more,
+        result.cursors.next.map(|c| c.encode()),
+        result.cursors.prev.map(|c| c.encode()),
+    );
+
+    Ok(Json(UsageAnomalyListResponse {
+        data: result.items,
+        pagination,
+    }))
+}
+
+/// Get a usage anomaly by ID
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/anomalies/{id}",
+    tag = "usage",
+    operation_id = "usage_anomaly_get",
+    params(("id" = Uuid, Path, description = "Usage anomaly ID")),
+    responses(
+        (status = 200, description = "Usage anomaly found", body = UsageAnomaly),
+        (status = 404, description = "Usage anomaly not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn get(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<UsageAnomaly>, AdminError> {
+    let service = get_service(&state)?;
+
+    let anomaly = service
+        .get_by_id(id)
+        .await?
+        .ok_or_else(|| AdminError::NotFound("Usage anomaly not found".to_string()))?;
+
+    let id_str = id.to_string();
+    let org_id = anomaly.org_id.map(|id| id.to_string());
+    authz.require(
+        "usage_anomaly",
+        "read",
+        Some(&id_str),
+        org_id.as_deref(),
+        None,
+        None,
+    )?;
+
+    Ok(Json(anomaly))
+}
+
+/// Acknowledge a usage anomaly
+///
+/// Marks the anomaly as reviewed and lifts the temporary rate limit it
+/// placed on the API key, if that is still in force. Acknowledging an
+/// anomaly twice keeps the first acknowledgement.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/admin/v1/anomalies/{id}/acknowledge",
+    tag = "usage",
+    operation_id = "usage_anomaly_acknowledge",
+    params(("id" = Uuid, Path, description = "Usage anomaly ID")),
+    responses(
+        (status = 200, description = "Usage anomaly acknowledged", body = UsageAnomaly),
+        (status = 404, description = "Usage anomaly not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn acknowledge(
+    State(state): State<AppState>,
+    Extension(admin_auth): Extension<AdminAuth>,
+    Extension(authz): Extension<AuthzContext>,
+    Extension(client_info): Extension<ClientInfo>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<UsageAnomaly>, AdminError> {
+    let services = state
+        .services
+        .as_ref()
+        .ok_or(AdminError::ServicesRequired)?;
+    let actor = AuditActor::from(&admin_auth);
+
+    let existing = services
+        .usage_anomalies
+        .get_by_id(id)
+        .await?
+        .ok_or_else(|| AdminError::NotFound("Usage anomaly not found".to_string()))?;
+
+    let id_str = id.to_string();
+    let org_id = existing.org_id.map(|id| id.to_string());
+    authz.require(
+        "usage_anomaly",
+        "update",
+        Some(&id_str),
+        org_id.as_deref(),
+        None,
+        None,
+    )?;
+
+    let anomaly = services
+        .usage_anomalies
+        .acknowledge(id, admin_auth.identity.user_id, state.cache.as_ref())
+        .await?
+        .ok_or_else(|| AdminError::NotFound("Usage anomaly not found".to_string()))?;
+
+    let _ = services
+        .audit_logs
+        .create(CreateAuditLog {
+            actor_type: actor.actor_type,
+            actor_id: actor.actor_id,
+            action: "usage_anomaly.acknowledge".to_string(),
+            resource_type: "usage_anomaly".to_string(),
+            resource_id: id,
+            org_id: anomaly.org_id,
+            project_id: None,
+            details: json!({
+                "kind": anomaly.kind,
+                "api_key_id": anomaly.api_key_id,
+                "rate_limited_until": anomaly.rate_limited_until,
+            }),
+            ip_address: client_info.ip_address,
+            user_agent: client_info.user_agent,
+        })
+        .await;
+
+    Ok(Json(anomaly))
+}
@@ -674,7 +674,9 @@ pub async fn remove_org_member(
 
     // Invalidate user sessions so they cannot continue accessing the org via SSO
     #[cfg(feature = "sso")]
-    invalidate_user_sessions(services, &state, user_id).await;
+    let sessions_revoked = invalidate_user_sessions(services, &state, user_id).await;
+    #[cfg(not(feature = "sso"))]
+    let sessions_revoked = 0usize;
 
     // Log audit event (fire-and-forget)
     let _ = services
@@ -691,6 +693,7 @@ pub async fn remove_org_member(
                 "user_id": user_id,
                 "org_slug": org_slug,
                 "org_name": org.name,
+                "sessions_revoked": sessions_revoked,
             }),
             ip_address: client_info.ip_address,
             user_agent: client_info.user_agent,
@@ -1091,27 +1094,31 @@ async fn invalidate_user_api_key_cache(services: &Services, state: &AppState, us
 /// Invalidate all SSO sessions for a user after org membership removal.
 ///
 /// This forces the user to re-authenticate, preventing continued access to
-/// the organization through stale browser sessions.
+/// the organization through stale browser sessions. Returns the number of
+/// sessions revoked so the caller can record it in the audit log.
 #[cfg(feature = "sso")]
-async fn invalidate_user_sessions(services: &Services, state: &AppState, user_id: Uuid) {
+async fn invalidate_user_sessions(services: &Services, state: &AppState, user_id: Uuid) -> usize {
     // Look up the user to get their external_id (needed for session store)
     let user = match services.users.get_by_id(user_id).await {
         Ok(Some(user)) if !user.external_id.is_empty() => user,
-        _ => return,
+        _ => return 0,
     };
 
     let session_store = match super::sessions::get_session_store(state) {
         Ok(store) => store,
-        Err(_) => return,
+        Err(_) => return 0,
     };
 
     match session_store.delete_user_sessions(&user.external_id).await {
-        Ok(count) if count > 0 => {
-            tracing::info!(
-                user_id = %user_id,
-                sessions_revoked = count,
-                "Revoked user sessions after org membership removal"
-            );
+        Ok(count) => {
+            if count > 0 {
+                tracing::info!(
+                    user_id = %user_id,
+                    sessions_revoked = count,
+                    "Revoked user sessions after org membership removal"
+                );
+            }
+            count
         }
         Err(e) => {
             tracing::warn!(
@@ -1119,7 +1126,7 @@ async fn invalidate_user_sessions(services: &Services, state: &AppState, user_id
                 user_id = %user_id,
                 "Failed to revoke user sessions after membership removal"
             );
+            0
         }
-        _ => {}
     }
 }
@@ -0,0 +1,193 @@
+//! Admin API endpoints for vector store sync history.
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::error::AdminError;
+use crate::{
+    AppState,
+    db::{Cursor, CursorDirection, ListParams},
+    middleware::AuthzContext,
+    models::{VectorStore, VectorStoreOwnerType, VectorStoreSync},
+    openapi::PaginationMeta,
+    services::Services,
+};
+
+/// Query parameters for listing sync runs.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema, utoipa::IntoParams))]
+pub struct VectorStoreSyncListQuery {
+    /// Maximum number of runs to return (default: 100).
+    pub limit: Option<i64>,
+    /// Cursor for keyset pagination. Encoded as base64 string.
+    #[cfg_attr(
+        feature = "utoipa",
+        schema(example = "MTczMzU4MDgwMDAwMDphYmMxMjM0NS02Nzg5LTAxMjMtNDU2Ny0wMTIzNDU2Nzg5YWI")
+    )]
+    pub cursor: Option<String>,
+    /// Pagination direction: "forward" (default) or "backward".
+    #[serde(default)]
+    pub direction: Option<String>,
+}
+
+impl VectorStoreSyncListQuery {
+    fn try_into_params(self) -> Result<ListParams, AdminError> {
+        let cursor = match &self.cursor {
+            Some(c) => Some(
+                Cursor::decode(c)
+                    .map_err(|e| AdminError::BadRequest(format!("Invalid cursor: {}", e)))?,
+            ),
+            None => None,
+        };
+
+        let direction = match self.direction.as_deref() {
+            Some("backward") => CursorDirection::Backward,
+            Some("forward") | None => CursorDirection::Forward,
+            Some(other) => {
+                return Err(AdminError::BadRequest(format!(
+                    "Invalid direction '{}': must be 'forward' or 'backward'",
+                    other
+                )));
+            }
+        };
+
+        Ok(ListParams {
+            limit: Some(self.limit.unwrap_or(100)),
+            cursor,
+            direction,
+            ..Default::default()
+        }
+        .clamp())
+    }
+}
+
+/// Paginated list of vector store sync runs
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct VectorStoreSyncListResponse {
+    /// List of sync runs, newest first
+    pub data: Vec<VectorStoreSync>,
+    /// Pagination metadata
+    pub pagination: PaginationMeta,
+}
+
+fn get_services(state: &AppState) -> Result<&Services, AdminError> {
+    state.services.as_ref().ok_or(AdminError::ServicesRequired)
+}
+
+/// Load the vector store and check the caller may read it. Sync history
+/// inherits the store's org/team/project scope.
+async fn authorize_store(
+    services: &Services,
+    authz: &AuthzContext,
+    vector_store_id: Uuid,
+) -> Result<VectorStore, AdminError> {
+    let store = services
+        .vector_stores
+        .get_by_id(vector_store_id)
+        .await?
+        .ok_or_else(|| AdminError::NotFound("Vector store not found".to_string()))?;
+
+    let owner_id = store.owner_id.to_string();
+    let (org_id, team_id, project_id) = match store.owner_type {
+        VectorStoreOwnerType::Organization => (Some(owner_id.as_str()), None, None),
+        VectorStoreOwnerType::Team => (None, Some(owner_id.as_str()), None),
+        VectorStoreOwnerType::Project => (None, None, Some(owner_id.as_str())),
+        VectorStoreOwnerType::User => (None, None, None),
+    };
+    let id_str = store.id.to_string();
+    authz.require(
+        "vector_store",
+        "read",
+        Some(&id_str),
+        org_id,
+        team_id,
+        project_id,
+    )?;
+
+    Ok(store)
+}
+
+/// List sync runs for a vector store
+///
+/// Returns runs of the `[features.vector_store_sync]` sources targeting this
+/// vector store, newest first.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/vector-stores/{id}/syncs",
+    tag = "vector-stores",
+    operation_id = "vector_store_sync_list",
+    params(
+        ("id" = Uuid, Path, description = "Vector store ID"),
+        VectorStoreSyncListQuery
+    ),
+    responses(
+        (status = 200, description = "List of sync runs", body = VectorStoreSyncListResponse),
+        (status = 400, description = "Invalid cursor or direction", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Vector store not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn list(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<VectorStoreSyncListQuery>,
+) -> Result<Json<VectorStoreSyncListResponse>, AdminError> {
+    let services = get_services(&state)?;
+    let params = query.try_into_params()?;
+    let limit = params.limit.unwrap_or(100);
+
+    authorize_store(services, &authz, id).await?;
+
+    let result = services.vector_store_syncs.list(id, params).await?;
+
+    let pagination = PaginationMeta::with_cursors(
+        limit,
+        result.has_more,
+        result.cursors.next.map(|c| c.encode()),
+        result.cursors.prev.map(|c| c.encode()),
+    );
+
+    Ok(Json(VectorStoreSyncListResponse {
+        data: result.items,
+        pagination,
+    }))
+}
+
+/// Get a vector store sync run
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/admin/v1/vector-stores/{id}/syncs/{sync_id}",
+    tag = "vector-stores",
+    operation_id = "vector_store_sync_get",
+    params(
+        ("id" = Uuid, Path, description = "Vector store ID"),
+        ("sync_id" = Uuid, Path, description = "Sync run ID"),
+    ),
+    responses(
+        (status = 200, description = "Sync run found", body = VectorStoreSync),
+        (status = 404, description = "Vector store or sync run not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+pub async fn get(
+    State(state): State<AppState>,
+    Extension(authz): Extension<AuthzContext>,
+    Path((id, sync_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<VectorStoreSync>, AdminError> {
+    let services = get_services(&state)?;
+
+    authorize_store(services, &authz, id).await?;
+
+    let sync = services
+        .vector_store_syncs
+        .get_by_id(sync_id)
+        .await?
+        .filter(|sync| sync.vector_store_id == id)
+        .ok_or_else(|| AdminError::NotFound("Sync run not found".to_string()))?;
+
+    Ok(Json(sync))
+}
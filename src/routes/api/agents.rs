@@ -0,0 +1,424 @@
+//! `/v1/agents/{agent_id}/runs` endpoints, running agents defined through
+//! the admin API:
+//!
+//! - `POST /v1/agents/{agent_id}/runs`           — run an agent
+//! - `GET  /v1/agents/{agent_id}/runs`           — list the caller's runs
+//! - `GET  /v1/agents/{agent_id}/runs/{run_id}`  — retrieve a run and its steps
+//!
+//! Agents belong to an organization and can only be run by its callers. A
+//! run's tool-use loop executes server-side; see
+//! [`crate::services::agents`] for how runs are recorded and billed.
+
+#![cfg(feature = "server")]
+
+use axum::{
+    Extension, Json,
+    body::Body,
+    extract::{Path, Query, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use bytes::Bytes;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use super::{ApiError, check_model_access};
+use crate::{
+    AppState,
+    auth::AuthenticatedRequest,
+    db::DbPool,
+    middleware::AuthzContext,
+    models::{
+        Agent, AgentRun, AgentRunList, AgentRunOutcome, AgentRunStatus, AgentRunWithSteps,
+        CreateAgentRun, CreateAgentRunRequest,
+    },
+    providers::Tenant,
+    routes::execution::{ResponsesExecutor, execute_with_fallback},
+    routing::{resolver, route_models_extended},
+    services::{
+        agents,
+        responses_pipeline::{PipelinePrincipal, apply_streaming_pipeline, derive_response_owner},
+    },
+};
+
+/// Query params for `GET /v1/agents/{agent_id}/runs`.
+#[derive(Debug, Deserialize)]
+pub struct ListAgentRunsQuery {
+    /// Page size. Clamped to `[1, 100]` (default 20).
+    #[serde(default)]
+    limit: Option<i64>,
+}
+
+fn get_db(state: &AppState) -> Result<&DbPool, ApiError> {
+    state.db.as_deref().ok_or_else(|| {
+        ApiError::new(
+            StatusCode::NOT_IMPLEMENTED,
+            "feature_not_available",
+            "Agents require a configured database",
+        )
+    })
+}
+
+async fn enforce_authz(
+    authz: Option<&Extension<AuthzContext>>,
+    auth: Option<&Extension<AuthenticatedRequest>>,
+    action: &str,
+) -> Result<(), ApiError> {
+    let Some(Extension(authz)) = authz else {
+        return Ok(());
+    };
+    let org_id = auth.and_then(|Extension(a)| a.effective_org_id().map(|id| id.to_string()));
+    let project_id =
+        auth.and_then(|Extension(a)| a.effective_project_id().map(|id| id.to_string()));
+    authz
+        .require_api(
+            "agent_run",
+            action,
+            None,
+            None,
+            org_id.as_deref(),
+            project_id.as_deref(),
+        )
+        .await
+        .map_err(|e| ApiError::new(StatusCode::FORBIDDEN, "authorization_denied", e.to_string()))
+}
+
+/// Load an agent of the caller's organization. Other organizations' agents
+/// are reported as missing.
+async fn load_agent(
+    state: &AppState,
+    db: &DbPool,
+    auth: Option<&Extension<AuthenticatedRequest>>,
+    agent_id: Uuid,
+) -> Result<Agent, ApiError> {
+    let org_id = auth
+        .and_then(|Extension(a)| a.effective_org_id())
+        .or(state.default_org_id);
+    db.agents()
+        .get_agent(agent_id)
+        .await?
+        .filter(|agent| Some(agent.org_id) == org_id)
+        .ok_or_else(|| {
+            ApiError::new(
+                StatusCode::NOT_FOUND,
+                "not_found",
+                format!("Agent '{agent_id}' not found"),
+            )
+        })
+}
+
+/// Load a run of `agent` the caller may see. Runs of other projects, when
+/// the caller has one, are reported as missing.
+async fn load_run(
+    db: &DbPool,
+    auth: Option<&Extension<AuthenticatedRequest>>,
+    agent: &Agent,
+    run_id: Uuid,
+) -> Result<AgentRun, ApiError> {
+    let project_id = auth.and_then(|Extension(a)| a.effective_project_id());
+    db.agents()
+        .get_run(run_id)
+        .await?
+        .filter(|run| {
+            run.agent_id == agent.id && (project_id.is_none() || run.project_id == project_id)
+        })
+        .ok_or_else(|| {
+            ApiError::new(
+                StatusCode::NOT_FOUND,
+                "not_found",
+                format!("Run '{run_id}' not found"),
+            )
+        })
+}
+
+/// Record a run that failed before its stream started.
+async fn fail_run(db: &DbPool, run: &AgentRun, error: String) {
+    let outcome = AgentRunOutcome {
+        status: AgentRunStatus::Failed,
+        output_text: None,
+        error: Some(error),
+        input_tokens: 0,
+        output_tokens: 0,
+        cost_microcents: None,
+    };
+    if let Err(e) = db.agents().finish_run(run.id, &outcome).await {
+        tracing::warn!(run_id = %run.id, error = %e, "Failed to record agent run failure");
+    }
+}
+
+/// Run an agent
+///
+/// Sends the input to the agent's model with its instructions and tools, and
+/// executes the tool calls the model makes server-side until it answers or
+/// the agent's `max_iterations` is reached. Each output item is stored as a
+/// step of the run.
+///
+/// With `stream: true` the response is a server-sent event stream: an
+/// `agent.run.created` event, the Responses API events of every turn, and an
+/// `agent.run.finished` event carrying the finished run. Otherwise the
+/// finished run is returned with its steps.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/api/v1/agents/{agent_id}/runs",
+    tag = "agents",
+    params(("agent_id" = Uuid, Path, description = "Agent ID")),
+    request_body = CreateAgentRunRequest,
+    responses(
+        (status = 200, description = "The finished run, or its event stream", body = AgentRunWithSteps),
+        (status = 400, description = "Invalid input", body = crate::openapi::ErrorResponse),
+        (status = 401, description = "Authentication required", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Model not allowed", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Agent not found", body = crate::openapi::ErrorResponse),
+        (status = 502, description = "Provider error", body = crate::openapi::ErrorResponse),
+    ),
+    security(("api_key" = []))
+))]
+pub async fn api_v1_agent_runs_create(
+    State(state): State<AppState>,
+    auth: Option<Extension<AuthenticatedRequest>>,
+    authz: Option<Extension<AuthzContext>>,
+    Path(agent_id): Path<Uuid>,
+    Json(request): Json<CreateAgentRunRequest>,
+) -> Result<Response, ApiError> {
+    let db_arc = state.db.clone();
+    let db = get_db(&state)?;
+    enforce_authz(authz.as_ref(), auth.as_ref(), "write").await?;
+    let agent = load_agent(&state, db, auth.as_ref(), agent_id).await?;
+    check_model_access(&state, auth.as_ref(), [agent.model.as_str()]).await?;
+    if let Some(Extension(ref auth)) = auth
+        && let Some(api_key) = auth.api_key()
+    {
+        api_key.check_model_allowed(&agent.model).map_err(|e| {
+            ApiError::new(StatusCode::FORBIDDEN, "model_not_allowed", e.to_string())
+        })?;
+    }
+
+    let mut payload = agents::build_payload(&agent, &request.input).map_err(|e| {
+        ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "invalid_request_error",
+            format!("Invalid input: {e}"),
+        )
+    })?;
+    let org_http_tools = crate::services::http_tool::resolve_org_http_tools(
+        &state,
+        &mut payload,
+        Some(agent.org_id),
+    )
+    .await?;
+
+    let auth_request = auth.as_ref().map(|e| &e.0);
+    let routed = route_models_extended(Some(agent.model.as_str()), None, &state.config.providers)?;
+    let resolved = resolver::resolve_to_provider(
+        routed,
+        state.db.as_ref(),
+        state.cache.as_ref(),
+        state.secrets.as_ref(),
+        &state.provider_settings,
+        auth_request,
+    )
+    .await?;
+    payload.model = Some(resolved.model.clone());
+
+    let shell_environment = payload
+        .tools
+        .iter()
+        .flatten()
+        .find_map(|t| t.as_shell())
+        .and_then(|shell| shell.environment.as_ref());
+    let resolved_shell_env = crate::services::shell_tool::resolve_shell_environment(
+        shell_environment,
+        &state.config.features.server_tools.shell_limits,
+        &state.config.features.containers,
+    )
+    .map_err(|e| {
+        ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "invalid_request_error",
+            e.to_string(),
+        )
+    })?;
+
+    let principal = PipelinePrincipal::from_auth(&state, auth_request);
+    let run = db
+        .agents()
+        .create_run(CreateAgentRun {
+            agent_id: agent.id,
+            org_id: agent.org_id,
+            project_id: principal.project_id,
+            user_id: principal.user_id,
+            api_key_id: principal.api_key_id,
+            service_account_id: principal.service_account_id,
+            provider: resolved.provider_name.clone(),
+            model: resolved.model.clone(),
+            input: request.input,
+        })
+        .await?;
+
+    let tenant = Tenant {
+        org_id: Some(agent.org_id),
+        project_id: principal.project_id,
+        api_key_id: principal.api_key_id,
+        ..Default::default()
+    };
+    let response = match execute_with_fallback::<ResponsesExecutor>(
+        &state,
+        resolved.provider_name.clone(),
+        resolved.provider_config.clone(),
+        resolved.model.clone(),
+        payload.clone(),
+        None,
+        &tenant,
+    )
+    .await
+    {
+        Ok(result) => result.response,
+        Err(e) => {
+            fail_run(db, &run, format!("{e:?}")).await;
+            return Err(e);
+        }
+    };
+    if !response.status().is_success() {
+        fail_run(
+            db,
+            &run,
+            format!("The provider returned status {}", response.status()),
+        )
+        .await;
+        return Ok(response);
+    }
+
+    let usage_entry = agents::usage_entry(&run, principal.team_id);
+    let response = apply_streaming_pipeline(
+        &state,
+        &payload,
+        resolved.provider_name.clone(),
+        resolved.provider_config,
+        resolved.model.clone(),
+        principal,
+        Vec::new(),
+        Vec::new(),
+        org_http_tools,
+        derive_response_owner(&state, auth_request),
+        None,
+        resolved_shell_env,
+        Some(run.id.to_string()),
+        response,
+        None,
+        agent.max_iterations.map(|n| n as usize),
+    );
+    let response =
+        crate::providers::inject_cost_into_response(crate::providers::CostInjectionParams {
+            response,
+            provider: &resolved.provider_name,
+            model: &resolved.model,
+            pricing: &state.pricing,
+            db: state.db.as_ref(),
+            usage_entry: Some(usage_entry),
+            task_tracker: Some(&state.task_tracker),
+            usage_drain: Some(&state.usage_drain),
+            max_response_body_bytes: state.config.server.max_response_body_bytes,
+            streaming_idle_timeout_secs: state.config.server.streaming_idle_timeout_secs,
+            validation_config: &state.config.observability.response_validation,
+            response_type: crate::validation::ResponseType::ResponseStream,
+        })
+        .await;
+    let (_parts, body) = response.into_parts();
+
+    if !request.stream {
+        let run = agents::record_run(db, run, body, None).await;
+        let steps = db.agents().list_steps(run.id).await?;
+        return Ok(Json(AgentRunWithSteps { run, steps }).into_response());
+    }
+
+    let (tx, rx) = tokio::sync::mpsc::channel::<Result<Bytes, std::io::Error>>(32);
+    let _ = tx
+        .send(Ok(agents::run_event("agent.run.created", &run)))
+        .await;
+    let db = db_arc.expect("database checked above");
+    crate::compat::spawn_detached(async move {
+        agents::record_run(&db, run, body, Some(tx)).await;
+    });
+
+    let stream = futures_util::stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|item| (item, rx))
+    });
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "text/event-stream")
+        .header(header::CACHE_CONTROL, "no-cache")
+        .body(Body::from_stream(stream))
+        .unwrap())
+}
+
+/// List an agent's runs
+///
+/// Lists the agent's runs, or only those of the caller's project when they
+/// have one, newest first.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/api/v1/agents/{agent_id}/runs",
+    tag = "agents",
+    params(
+        ("agent_id" = Uuid, Path, description = "Agent ID"),
+        ("limit" = Option<i64>, Query, description = "Page size, clamped to 1..=100 (default 20)"),
+    ),
+    responses(
+        (status = 200, description = "The agent's runs", body = AgentRunList),
+        (status = 401, description = "Authentication required", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Agent not found", body = crate::openapi::ErrorResponse),
+    ),
+    security(("api_key" = []))
+))]
+pub async fn api_v1_agent_runs_list(
+    State(state): State<AppState>,
+    auth: Option<Extension<AuthenticatedRequest>>,
+    authz: Option<Extension<AuthzContext>>,
+    Path(agent_id): Path<Uuid>,
+    Query(params): Query<ListAgentRunsQuery>,
+) -> Result<Json<AgentRunList>, ApiError> {
+    let db = get_db(&state)?;
+    enforce_authz(authz.as_ref(), auth.as_ref(), "read").await?;
+    let agent = load_agent(&state, db, auth.as_ref(), agent_id).await?;
+    let project_id = auth
+        .as_ref()
+        .and_then(|Extension(a)| a.effective_project_id());
+
+    let limit = params.limit.unwrap_or(20).clamp(1, 100);
+    let data = db.agents().list_runs(agent.id, project_id, limit).await?;
+    Ok(Json(AgentRunList { data }))
+}
+
+/// Retrieve a run
+///
+/// Returns the run with its steps: the messages, tool calls and tool outputs
+/// it produced, in order. Steps of a run still in progress are those
+/// recorded so far.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/api/v1/agents/{agent_id}/runs/{run_id}",
+    tag = "agents",
+    params(
+        ("agent_id" = Uuid, Path, description = "Agent ID"),
+        ("run_id" = Uuid, Path, description = "Run ID"),
+    ),
+    responses(
+        (status = 200, description = "The run", body = AgentRunWithSteps),
+        (status = 401, description = "Authentication required", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Agent or run not found", body = crate::openapi::ErrorResponse),
+    ),
+    security(("api_key" = []))
+))]
+pub async fn api_v1_agent_runs_get(
+    State(state): State<AppState>,
+    auth: Option<Extension<AuthenticatedRequest>>,
+    authz: Option<Extension<AuthzContext>>,
+    Path((agent_id, run_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<AgentRunWithSteps>, ApiError> {
+    let db = get_db(&state)?;
+    enforce_authz(authz.as_ref(), auth.as_ref(), "read").await?;
+    let agent = load_agent(&state, db, auth.as_ref(), agent_id).await?;
+    let run = load_run(db, auth.as_ref(), &agent, run_id).await?;
+    let steps = db.agents().list_steps(run.id).await?;
+    Ok(Json(AgentRunWithSteps { run, steps }))
+}
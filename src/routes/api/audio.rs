@@ -4,7 +4,7 @@ use axum::{Extension, Json, body::Bytes, extract::State, response::Response};
 use axum_valid::Valid;
 use http::StatusCode;
 
-use super::{ApiError, check_sovereignty, voice_to_string};
+use super::{ApiError, check_model_access, check_sovereignty, voice_to_string};
 #[cfg(feature = "provider-azure")]
 use crate::providers::azure_openai;
 use crate::{
@@ -52,6 +52,7 @@ pub async fn api_v1_audio_speech(
 
     // Route the model to a provider
     let model = Some(payload.model.clone());
+    check_model_access(&state, auth.as_ref(), model.as_deref()).await?;
     let routed = route_model_extended(model.as_deref(), &state.config.providers)?;
 
     // Resolve to concrete provider configuration
@@ -60,6 +61,7 @@ pub async fn api_v1_audio_speech(
         state.db.as_ref(),
         state.cache.as_ref(),
         state.secrets.as_ref(),
+        &state.provider_settings,
         auth.as_ref().map(|e| &e.0),
     )
     .await
@@ -127,12 +129,13 @@ pub async fn api_v1_audio_speech(
 
     // Check sovereignty requirements (API key + per-request)
     let _sovereignty_reqs = check_sovereignty(
+        &state,
         auth.as_ref(),
         payload.sovereignty_requirements.as_ref(),
         &provider_config,
         &model_name,
-        &state.model_catalog,
-    )?;
+    )
+    .await?;
 
     // Replace model with resolved name (strip provider prefix)
     let mut payload = payload;
@@ -417,6 +420,7 @@ pub async fn api_v1_audio_transcriptions(
     };
 
     // Route the model to a provider
+    check_model_access(&state, auth.as_ref(), [model.as_str()]).await?;
     let routed = route_model_extended(Some(&model), &state.config.providers)?;
 
     // Resolve to concrete provider configuration
@@ -425,6 +429,7 @@ pub async fn api_v1_audio_transcriptions(
         state.db.as_ref(),
         state.cache.as_ref(),
         state.secrets.as_ref(),
+        &state.provider_settings,
         auth.as_ref().map(|e| &e.0),
     )
     .await
@@ -493,12 +498,13 @@ pub async fn api_v1_audio_transcriptions(
 
     // Check sovereignty requirements (API key + per-request)
     let _sovereignty_reqs = check_sovereignty(
+        &state,
         auth.as_ref(),
         sovereignty_requirements.as_ref(),
         &provider_config,
         &model_name,
-        &state.model_catalog,
-    )?;
+    )
+    .await?;
 
     // Replace model with resolved name (strip provider prefix)
     let mut request = request;
@@ -745,6 +751,7 @@ pub async fn api_v1_audio_translations(
     };
 
     // Route the model to a provider
+    check_model_access(&state, auth.as_ref(), [model.as_str()]).await?;
     let routed = route_model_extended(Some(&model), &state.config.providers)?;
 
     // Resolve to concrete provider configuration
@@ -753,6 +760,7 @@ pub async fn api_v1_audio_translations(
         state.db.as_ref(),
         state.cache.as_ref(),
         state.secrets.as_ref(),
+        &state.provider_settings,
         auth.as_ref().map(|e| &e.0),
     )
     .await
@@ -817,12 +825,13 @@ pub async fn api_v1_audio_translations(
 
     // Check sovereignty requirements (API key + per-request)
     let _sovereignty_reqs = check_sovereignty(
+        &state,
         auth.as_ref(),
         sovereignty_requirements.as_ref(),
         &provider_config,
         &model_name,
-        &state.model_catalog,
-    )?;
+    )
+    .await?;
 
     // Replace model with resolved name (strip provider prefix)
     let mut request = request;
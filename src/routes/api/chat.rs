@@ -6,9 +6,12 @@ use chrono::Utc;
 use http::StatusCode;
 
 use super::{
-    ApiError, check_sovereignty, log_guardrails_evaluation, log_output_guardrails_evaluation,
-    messages_contain_images, reasoning_effort_to_string, response_format_to_string,
-    responses_reasoning_effort_to_string, should_bypass_cache,
+    ApiError, api_key_allows, apply_chat_defaults, apply_completion_defaults,
+    apply_responses_defaults, available_rung, check_capabilities, check_context_window,
+    check_entitlement, check_model_access, check_sovereignty, log_guardrails_evaluation,
+    log_output_guardrails_evaluation, messages_contain_images, reasoning_effort_to_string,
+    resolve_entitlements, resolve_model_degradation, resolve_request_defaults,
+    response_format_to_string, responses_reasoning_effort_to_string, should_bypass_cache,
 };
 #[cfg(feature = "server")]
 use crate::services::response_persister::persist_non_streaming;
@@ -17,13 +20,21 @@ use crate::{
     auth::AuthenticatedRequest,
     authz::RequestContext,
     cache::{CacheLookupResult, CacheTenantScope, SemanticLookupResult, StoreParams},
+    config::{ProviderConfig, RequestPriority, SovereigntyRequirements},
     middleware::{AuthzContext, ClientInfo, RequestId},
-    models::UsageLogEntry,
+    models::{DegradationLadder, DegradationTrigger, EntitledFeature, UsageLogEntry},
+    providers::Tenant,
     routes::execution::{
         ChatCompletionExecutor, CompactExecutor, CompletionExecutor, ExecutionResult,
-        ProviderExecutor, ResponsesExecutor, execute_with_fallback,
+        ResponsesExecutor, execute_provider, execute_with_fallback,
     },
     routing::{resolver, route_model_extended, route_models_extended},
+    services::{
+        context_compression,
+        model_degradation::{self, BudgetPressure, Degradation},
+        structured_output::{self, DiscardedUsage, SchemaValidator},
+    },
+    validation::capabilities::RequestFeatures,
 };
 
 /// Cache status for tracking cache hits/misses in response headers.
@@ -35,20 +46,60 @@ pub(super) enum CacheStatus {
     Miss,
 }
 
-/// Build a tenant scope from the optional API-key auth, used to key cache
-/// entries so two tenants never share a response/embedding cache hit.
+/// Build a tenant scope from the request's auth, used to key cache entries so
+/// two tenants never share a response/embedding cache hit.
+///
+/// API keys are scoped by their own org/project/owner. Other identities
+/// (sessions, JWTs, client certs) fall back to their org and user, so they
+/// don't share the unscoped namespace of unauthenticated traffic.
 pub(super) fn tenant_scope_from_auth(
     auth: Option<&Extension<AuthenticatedRequest>>,
 ) -> CacheTenantScope {
-    let api_key = auth.and_then(|a| a.api_key());
+    let auth = auth.map(|e| &e.0);
+    if let Some(api_key) = auth.and_then(|a| a.api_key()) {
+        return CacheTenantScope {
+            org_id: api_key.org_id.map(|id| id.to_string()),
+            project_id: api_key.project_id.map(|id| id.to_string()),
+            api_key_id: Some(api_key.key.id.to_string()),
+            user_id: match &api_key.key.owner {
+                crate::models::ApiKeyOwner::User { user_id } => Some(user_id.to_string()),
+                _ => None,
+            },
+        };
+    }
     CacheTenantScope {
-        org_id: api_key.and_then(|k| k.org_id.map(|id| id.to_string())),
-        project_id: api_key.and_then(|k| k.project_id.map(|id| id.to_string())),
-        api_key_id: api_key.map(|k| k.key.id.to_string()),
-        user_id: api_key.and_then(|k| match &k.key.owner {
-            crate::models::ApiKeyOwner::User { user_id } => Some(user_id.to_string()),
-            _ => None,
-        }),
+        org_id: auth
+            .and_then(|a| a.org_id().or_else(|| a.principal().org_id()))
+            .map(|id| id.to_string()),
+        project_id: auth.and_then(|a| a.project_id()).map(|id| id.to_string()),
+        api_key_id: None,
+        user_id: auth.and_then(|a| a.user_id()).map(|id| id.to_string()),
+    }
+}
+
+/// Org/project a provider request is queued under when the provider has a
+/// `fair_queue`, along with what sets its priority: the API key's tier and
+/// the `x-priority` header. Unrecognized header values are ignored.
+pub(super) fn fair_queue_tenant(
+    state: &AppState,
+    auth: Option<&Extension<AuthenticatedRequest>>,
+    headers: &HeaderMap,
+) -> Tenant {
+    let auth = auth.map(|e| &e.0);
+    Tenant {
+        org_id: auth
+            .and_then(|a| {
+                a.api_key()
+                    .and_then(|k| k.org_id)
+                    .or_else(|| a.principal().org_id())
+            })
+            .or(state.default_org_id),
+        project_id: auth.and_then(|a| a.project_id()),
+        api_key_id: auth.and_then(|a| a.api_key()).map(|k| k.key.id),
+        priority: headers
+            .get("X-Priority")
+            .and_then(|v| v.to_str().ok())
+            .and_then(RequestPriority::parse),
     }
 }
 
@@ -470,8 +521,14 @@ pub(super) fn build_streaming_usage_entry(
     state: &AppState,
     model: &str,
     provider: &str,
-    header_project_id: Option<uuid::Uuid>,
+    headers: &HeaderMap,
 ) -> Option<UsageLogEntry> {
+    let header_project_id = headers
+        .get("X-Hadrian-Project")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| uuid::Uuid::parse_str(v).ok());
+    let idempotency_key = crate::services::idempotency::key_from_headers(headers);
+    let tags = crate::services::usage_tags::from_headers(headers);
     if let Some(Extension(auth)) = auth {
         let api_key = auth.api_key();
         Some(UsageLogEntry {
@@ -481,7 +538,7 @@ pub(super) fn build_streaming_usage_entry(
             org_id: api_key
                 .and_then(|k| k.org_id)
                 .or_else(|| auth.principal().org_id()),
-            project_id: api_key.and_then(|k| k.project_id).or(header_project_id),
+            project_id: auth.project_id().or(header_project_id),
             team_id: api_key.and_then(|k| k.team_id),
             service_account_id: api_key.and_then(|k| k.service_account_id),
             model: model.to_string(),
@@ -511,6 +568,21 @@ pub(super) fn build_streaming_usage_entry(
             tool_results_count: None,
             tool_runtime_seconds: None,
             tool_exit_code: None,
+            jwt_subject: auth.bearer_jwt().map(|jwt| jwt.subject.clone()),
+            error_code: None,
+            structured_output_repairs: None,
+            context_original_tokens: None,
+            context_compressed_tokens: None,
+            degraded_from_model: None,
+            degradation_reason: None,
+            ttft_ms: None,
+            inter_token_latency_ms: None,
+            output_tokens_per_second: None,
+            idempotency_key,
+            // Filled in from the provider response by cost injection
+            provider_attempts: None,
+            suspected_duplicate_attempts: None,
+            tags,
         })
     } else if state.default_user_id.is_some() || state.default_org_id.is_some() {
         // Anonymous mode: attribute to the default user/org so streaming usage
@@ -550,6 +622,21 @@ pub(super) fn build_streaming_usage_entry(
             tool_results_count: None,
             tool_runtime_seconds: None,
             tool_exit_code: None,
+            jwt_subject: None,
+            error_code: None,
+            structured_output_repairs: None,
+            context_original_tokens: None,
+            context_compressed_tokens: None,
+            degraded_from_model: None,
+            degradation_reason: None,
+            ttft_ms: None,
+            inter_token_latency_ms: None,
+            output_tokens_per_second: None,
+            idempotency_key,
+            // Filled in from the provider response by cost injection
+            provider_attempts: None,
+            suspected_duplicate_attempts: None,
+            tags,
         })
     } else {
         None
@@ -765,12 +852,66 @@ pub async fn api_v1_chat_completions(
     authz: Option<Extension<AuthzContext>>,
     request_id: Option<Extension<RequestId>>,
     client_info: Option<Extension<ClientInfo>>,
+    budget_pressure: Option<Extension<BudgetPressure>>,
     Valid(Json(mut payload)): Valid<Json<api_types::CreateChatCompletionPayload>>,
 ) -> Result<Response, ApiError> {
     let (ci_ip, ci_ua) = client_info
         .map(|Extension(ci)| (ci.ip_address, ci.user_agent))
         .unwrap_or_default();
 
+    // Fill in org/project defaults for anything the client left unset
+    let defaults = resolve_request_defaults(&state, auth.as_ref()).await;
+    if let Some(defaults) = &defaults {
+        apply_chat_defaults(&mut payload, defaults);
+    }
+
+    check_model_access(
+        &state,
+        auth.as_ref(),
+        payload
+            .model
+            .iter()
+            .chain(payload.models.iter().flatten())
+            .map(String::as_str),
+    )
+    .await?;
+
+    // Move the request down its model's degradation ladder if the model's
+    // provider is down or the caller is nearing its budget
+    let requested_model = payload.model.clone();
+    let degradation_policy = resolve_model_degradation(&state, auth.as_ref()).await;
+    let degradation_ladder = degradation_policy
+        .as_ref()
+        .zip(requested_model.as_deref())
+        .and_then(|(policy, model)| policy.ladder_for(model));
+    let mut degradation = None;
+    if let (Some(policy), Some(ladder), Some(requested)) = (
+        degradation_policy.as_ref(),
+        degradation_ladder,
+        requested_model.as_deref(),
+    ) && api_key_allows(auth.as_ref(), requested)
+        && let Some(reason) = model_degradation::pre_dispatch_trigger(
+            &state,
+            policy,
+            requested,
+            budget_pressure.is_some(),
+        )
+        && let Some((rung, model)) = available_rung(&state, auth.as_ref(), ladder, 0).await
+    {
+        tracing::info!(
+            from = %requested,
+            to = %model,
+            reason = reason.as_str(),
+            "Degrading chat completion model"
+        );
+        payload.model = Some(model);
+        degradation = Some(Degradation {
+            from_model: requested.to_string(),
+            rung,
+            reason,
+        });
+    }
+
     // Route the model to a provider with dynamic support
     let model_clone = payload.model.clone();
     let is_streaming = payload.stream;
@@ -782,6 +923,7 @@ pub async fn api_v1_chat_completions(
         state.db.as_ref(),
         state.cache.as_ref(),
         state.secrets.as_ref(),
+        &state.provider_settings,
         auth.as_ref().map(|e| &e.0),
     )
     .await
@@ -872,12 +1014,21 @@ pub async fn api_v1_chat_completions(
 
     // Check sovereignty requirements (API key + per-request)
     let sovereignty_reqs = check_sovereignty(
+        &state,
         auth.as_ref(),
         payload.sovereignty_requirements.as_ref(),
         &provider_config,
         &model_name,
-        &state.model_catalog,
-    )?;
+    )
+    .await?;
+
+    // Reject features the model does not support before calling the provider.
+    // Structured output is enforced by the gateway when repairs are enabled.
+    let mut features = RequestFeatures::for_chat(&payload);
+    if state.config.features.structured_outputs.enabled && !is_streaming {
+        features.structured_output = false;
+    }
+    check_capabilities(&state, &provider_config, &model_name, features)?;
 
     // Check if input guardrails are configured and what mode they're in
     let use_concurrent_guardrails = state
@@ -972,9 +1123,24 @@ pub async fn api_v1_chat_completions(
         .map(|c| &c.key_components);
 
     let cache_tenant = tenant_scope_from_auth(auth.as_ref());
+    let queue_tenant = fair_queue_tenant(&state, auth.as_ref(), &headers);
+
+    // Semantic matching depends on the organization's plan; organizations
+    // without it use the exact-match response cache
+    let semantic_cache = match &state.semantic_cache {
+        Some(cache) => match resolve_entitlements(&state, auth.as_ref()).await {
+            Ok(entitlements)
+                if entitlements.is_none_or(|e| e.allows(EntitledFeature::SemanticCache)) =>
+            {
+                Some(cache)
+            }
+            _ => None,
+        },
+        None => None,
+    };
 
     // Check semantic cache first (if available), then fall back to simple response cache
-    if let Some(ref semantic_cache) = state.semantic_cache {
+    if let Some(semantic_cache) = semantic_cache {
         let key_components = key_components.cloned().unwrap_or_default();
         match semantic_cache
             .lookup(
@@ -1034,6 +1200,29 @@ pub async fn api_v1_chat_completions(
             .lookup(&payload, &model_name, &cache_tenant, force_refresh)
             .await
         {
+            CacheLookupResult::Hit(cached) if is_streaming => {
+                let include_usage = payload
+                    .stream_options
+                    .as_ref()
+                    .is_some_and(|o| o.include_usage);
+                if let Some(body) = response_cache.replay_stream(&cached, include_usage) {
+                    tracing::debug!(
+                        model = %model_name,
+                        provider = %cached.provider,
+                        cached_at = cached.cached_at,
+                        "Replaying cached response as stream"
+                    );
+                    return Ok(Response::builder()
+                        .status(StatusCode::OK)
+                        .header("Content-Type", "text/event-stream")
+                        .header("Cache-Control", "no-cache")
+                        .header("X-Cache", "HIT")
+                        .header("X-Cached-At", cached.cached_at.to_string())
+                        .body(body)
+                        .unwrap());
+                }
+                tracing::debug!("Cached response is not a chat completion, skipping replay");
+            }
             CacheLookupResult::Hit(cached) => {
                 tracing::debug!(
                     model = %model_name,
@@ -1058,6 +1247,77 @@ pub async fn api_v1_chat_completions(
         }
     }
 
+    // Compress long conversations before dispatch. The response cache keeps
+    // keying on the conversation as sent, which is what later lookups see.
+    let compression = context_compression::compress(
+        &state,
+        &mut payload,
+        context_compression::RequestRoute {
+            provider_name: &provider_name,
+            provider_config: &provider_config,
+            model_name: &model_name,
+            tenant: &queue_tenant,
+        },
+    )
+    .await;
+
+    // Reject conversations that still don't fit the model's context window
+    check_context_window(
+        &state,
+        &provider_config,
+        model_clone.as_deref().unwrap_or(&model_name),
+        &model_name,
+        &payload,
+        "messages",
+    )?;
+
+    let cache_payload = compression
+        .as_ref()
+        .map(|c| api_types::CreateChatCompletionPayload {
+            messages: c.original_messages.clone(),
+            ..payload.clone()
+        });
+
+    // Compile the response schema before the payload is consumed, so the
+    // result can be validated and repaired
+    let structured_output = match SchemaValidator::for_payload(&payload) {
+        Some(compiled) if state.config.features.structured_outputs.enabled && !is_streaming => {
+            let validator = compiled.map_err(|e| {
+                ApiError::new(
+                    StatusCode::BAD_REQUEST,
+                    "invalid_json_schema",
+                    format!("response_format.json_schema.schema is invalid: {e}"),
+                )
+            })?;
+            let requested = headers
+                .get(structured_output::MAX_REPAIRS_HEADER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse().ok());
+            let repairs = state.config.features.structured_outputs.repairs_for(
+                requested,
+                defaults.as_ref().and_then(|d| d.structured_output_repairs),
+            );
+            let route = RepairRoute {
+                provider_name: provider_name.clone(),
+                provider_config: provider_config.clone(),
+                model_name: model_name.clone(),
+                sovereignty_reqs: sovereignty_reqs.clone(),
+                tenant: queue_tenant,
+            };
+            Some((validator, repairs, payload.clone(), route))
+        }
+        _ => None,
+    };
+
+    // Pick non-streaming requests for shadow traffic before the payload is consumed
+    #[cfg(feature = "server")]
+    let shadow = state
+        .shadow_traffic
+        .as_ref()
+        .filter(|_| !is_streaming)
+        .and_then(|shadow| shadow.select(model_clone.as_deref(), &provider_name, &model_name))
+        .map(|selection| (selection, payload.clone(), std::time::Instant::now()));
+
     // Execute request with fallback support
     // In concurrent guardrails mode, we race the guardrails evaluation with the LLM call
     let (response, provider_name, model_name) = if use_concurrent_guardrails {
@@ -1091,6 +1351,7 @@ pub async fn api_v1_chat_completions(
                 llm_model_name,
                 llm_payload,
                 llm_sovereignty_reqs.as_ref(),
+                &queue_tenant,
             )
             .await
         };
@@ -1158,11 +1419,73 @@ pub async fn api_v1_chat_completions(
             model_name,
             payload.clone(),
             sovereignty_reqs.as_ref(),
+            &queue_tenant,
         )
         .await?;
         (response, provider_name, model_name)
     };
 
+    // Retry rate limited requests further down the degradation ladder
+    let (response, provider_name, model_name) = if response.status()
+        == StatusCode::TOO_MANY_REQUESTS
+        && let (Some(policy), Some(ladder), Some(requested)) = (
+            degradation_policy.as_ref(),
+            degradation_ladder,
+            requested_model.as_deref(),
+        )
+        && policy.triggers_on(DegradationTrigger::RateLimited)
+        && api_key_allows(auth.as_ref(), requested)
+        && let Some((result, rung)) = retry_degraded(
+            &state,
+            auth.as_ref(),
+            &payload,
+            ladder,
+            degradation.as_ref().map_or(0, |d| d.rung + 1),
+            &queue_tenant,
+        )
+        .await
+    {
+        tracing::info!(
+            from = %requested,
+            to = %ladder.downgrades[rung],
+            "Degrading rate limited chat completion model"
+        );
+        degradation = Some(Degradation {
+            from_model: requested.to_string(),
+            rung,
+            reason: DegradationTrigger::RateLimited,
+        });
+        (result.response, result.provider_name, result.model_name)
+    } else {
+        (response, provider_name, model_name)
+    };
+
+    // Validate structured output, re-prompting with the errors if needed
+    let (response, provider_name, model_name, structured_outcome) =
+        if let Some((validator, repairs, repair_payload, route)) = structured_output {
+            let (result, outcome) = enforce_structured_output(
+                &state,
+                &validator,
+                repairs,
+                repair_payload,
+                &route,
+                ExecutionResult {
+                    response,
+                    provider_name,
+                    model_name,
+                },
+            )
+            .await?;
+            (
+                result.response,
+                result.provider_name,
+                result.model_name,
+                outcome,
+            )
+        } else {
+            (response, provider_name, model_name, None)
+        };
+
     // Apply output guardrails if configured
     let (response, output_guardrails_headers) = if let Some(ref output_guardrails) =
         state.output_guardrails
@@ -1189,7 +1512,13 @@ pub async fn api_v1_chat_completions(
 
     // Cache the RAW response BEFORE cost injection (if applicable)
     // This ensures cached responses don't have stale pricing and cost $0 on replay
-    let response = if cache_status == CacheStatus::Miss && response.status().is_success() {
+    // Streamed responses aren't stored; they can only be served by replay
+    // Output that still fails its schema after repairs isn't cached
+    let response = if cache_status == CacheStatus::Miss
+        && !is_streaming
+        && response.status().is_success()
+        && structured_outcome.is_none_or(|o| o.valid)
+    {
         // Extract content-type and body for caching
         let content_type = response
             .headers()
@@ -1205,9 +1534,9 @@ pub async fn api_v1_chat_completions(
                 let body_vec = bytes.to_vec();
 
                 // Store in semantic cache if available, otherwise in response cache
-                if let Some(ref semantic_cache) = state.semantic_cache {
+                if let Some(semantic_cache) = semantic_cache {
                     let cache = semantic_cache.clone();
-                    let payload_clone = payload.clone();
+                    let payload_clone = cache_payload.clone().unwrap_or_else(|| payload.clone());
                     let model_clone = model_name.clone();
                     let provider_clone = provider_name.clone();
                     let content_type_clone = content_type.clone();
@@ -1242,7 +1571,7 @@ pub async fn api_v1_chat_completions(
                     });
                 } else if let Some(ref response_cache) = state.response_cache {
                     let cache = response_cache.clone();
-                    let payload_clone = payload.clone();
+                    let payload_clone = cache_payload.clone().unwrap_or_else(|| payload.clone());
                     let model_clone = model_name.clone();
                     let provider_clone = provider_name.clone();
                     let content_type_clone = content_type;
@@ -1281,12 +1610,21 @@ pub async fn api_v1_chat_completions(
 
     // Create usage entry for streaming cost tracking
     let usage_entry = if is_streaming {
-        build_streaming_usage_entry(&auth, &state, &model_name, &provider_name, {
-            headers
-                .get("X-Hadrian-Project")
-                .and_then(|v| v.to_str().ok())
-                .and_then(|v| uuid::Uuid::parse_str(v).ok())
-        })
+        build_streaming_usage_entry(&auth, &state, &model_name, &provider_name, &headers).map(
+            |mut entry| {
+                if let Some(compression) = &compression {
+                    entry.context_original_tokens =
+                        Some(i32::try_from(compression.original_tokens).unwrap_or(i32::MAX));
+                    entry.context_compressed_tokens =
+                        Some(i32::try_from(compression.compressed_tokens).unwrap_or(i32::MAX));
+                }
+                if let Some(degradation) = &degradation {
+                    entry.degraded_from_model = Some(degradation.from_model.clone());
+                    entry.degradation_reason = Some(degradation.reason.as_str().to_string());
+                }
+                entry
+            },
+        )
     } else {
         None
     };
@@ -1352,9 +1690,299 @@ pub async fn api_v1_chat_completions(
         }
     }
 
+    if let Some(compression) = &compression {
+        final_response.headers_mut().insert(
+            context_compression::ORIGINAL_TOKENS_HEADER,
+            compression.original_tokens.into(),
+        );
+        final_response.headers_mut().insert(
+            context_compression::COMPRESSED_TOKENS_HEADER,
+            compression.compressed_tokens.into(),
+        );
+    }
+
+    if let Some(degradation) = &degradation {
+        degradation.insert_headers(final_response.headers_mut());
+    }
+
+    if let Some(outcome) = structured_outcome {
+        final_response.headers_mut().insert(
+            structured_output::REPAIRS_HEADER,
+            outcome.repairs.to_string().parse().unwrap(),
+        );
+        final_response.headers_mut().insert(
+            structured_output::VALID_HEADER,
+            outcome.valid.to_string().parse().unwrap(),
+        );
+    }
+
+    #[cfg(feature = "server")]
+    if let Some((selection, shadow_payload, started)) = shadow
+        && let Some(shadow_traffic) = state.shadow_traffic.as_ref()
+    {
+        final_response = mirror_to_shadow(
+            &state,
+            shadow_traffic,
+            selection,
+            shadow_payload,
+            queue_tenant,
+            final_response,
+            &provider_name,
+            &model_name,
+            started.elapsed(),
+        )
+        .await;
+    }
+
     Ok(final_response)
 }
 
+/// Retry a rate limited chat completion on the rungs of its degradation
+/// ladder from `start`, returning the first response that isn't rate limited
+/// along with its rung.
+///
+/// Rungs that can't be routed, fail the caller's sovereignty requirements or
+/// fail outright are skipped.
+async fn retry_degraded(
+    state: &AppState,
+    auth: Option<&Extension<AuthenticatedRequest>>,
+    payload: &api_types::CreateChatCompletionPayload,
+    ladder: &DegradationLadder,
+    mut start: usize,
+    tenant: &Tenant,
+) -> Option<(ExecutionResult, usize)> {
+    while let Some((rung, model)) = available_rung(state, auth, ladder, start).await {
+        start = rung + 1;
+        let Ok(routed) = route_model_extended(Some(&model), &state.config.providers) else {
+            continue;
+        };
+        let Ok(resolved) = resolver::resolve_to_provider(
+            routed,
+            state.db.as_ref(),
+            state.cache.as_ref(),
+            state.secrets.as_ref(),
+            &state.provider_settings,
+            auth.map(|e| &e.0),
+        )
+        .await
+        else {
+            continue;
+        };
+        let Ok(sovereignty_reqs) = check_sovereignty(
+            state,
+            auth,
+            payload.sovereignty_requirements.as_ref(),
+            &resolved.provider_config,
+            &resolved.model,
+        )
+        .await
+        else {
+            continue;
+        };
+
+        let mut payload = payload.clone();
+        payload.model = Some(resolved.model.clone());
+        match execute_with_fallback::<ChatCompletionExecutor>(
+            state,
+            resolved.provider_name,
+            resolved.provider_config,
+            resolved.model,
+            payload,
+            sovereignty_reqs.as_ref(),
+            tenant,
+        )
+        .await
+        {
+            Ok(result) if result.response.status() != StatusCode::TOO_MANY_REQUESTS => {
+                return Some((result, rung));
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::debug!(model = %model, error = ?e, "Degraded chat completion failed");
+            }
+        }
+    }
+    None
+}
+
+/// Where structured output repairs are sent: the request's original route,
+/// so repairs get the same fallbacks as the first attempt.
+struct RepairRoute {
+    provider_name: String,
+    provider_config: ProviderConfig,
+    model_name: String,
+    sovereignty_reqs: Option<SovereigntyRequirements>,
+    tenant: Tenant,
+}
+
+/// Result of validating a `json_schema` response.
+#[derive(Debug, Clone, Copy)]
+struct StructuredOutputOutcome {
+    /// Repair attempts made
+    repairs: u32,
+    /// Whether the returned content matches the schema
+    valid: bool,
+}
+
+/// Validate a `json_schema` response, re-prompting with the validation
+/// errors until the output matches or `max_repairs` attempts are spent.
+///
+/// The last response is returned either way; if a repair attempt fails, the
+/// response before it is. Usage of discarded attempts is added to the
+/// returned body. The outcome is `None` when the first response wasn't
+/// successful, since there was nothing to validate.
+async fn enforce_structured_output(
+    state: &AppState,
+    validator: &SchemaValidator,
+    max_repairs: u32,
+    mut payload: api_types::CreateChatCompletionPayload,
+    route: &RepairRoute,
+    result: ExecutionResult,
+) -> Result<(ExecutionResult, Option<StructuredOutputOutcome>), ApiError> {
+    if !result.response.status().is_success() {
+        return Ok((result, None));
+    }
+    let ExecutionResult {
+        response,
+        mut provider_name,
+        mut model_name,
+    } = result;
+    let (mut parts, body) = response.into_parts();
+    let mut bytes = read_structured_output_body(state, body).await?;
+    let mut discarded = DiscardedUsage::default();
+    let mut repairs = 0;
+
+    let (mut body, valid) = loop {
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap_or_default();
+        let Some(content) = structured_output::completion_content(&body) else {
+            break (body, true);
+        };
+        let errors = validator.validate(content);
+        if errors.is_empty() || repairs == max_repairs {
+            break (body, errors.is_empty());
+        }
+
+        repairs += 1;
+        tracing::debug!(
+            provider = %provider_name,
+            model = %model_name,
+            attempt = repairs,
+            errors = ?errors,
+            "Structured output failed schema validation, repairing"
+        );
+        structured_output::add_repair_prompt(&mut payload, content, &errors);
+        let next = match execute_with_fallback::<ChatCompletionExecutor>(
+            state,
+            route.provider_name.clone(),
+            route.provider_config.clone(),
+            route.model_name.clone(),
+            payload.clone(),
+            route.sovereignty_reqs.as_ref(),
+            &route.tenant,
+        )
+        .await
+        {
+            Ok(next) if next.response.status().is_success() => next,
+            Ok(next) => {
+                tracing::warn!(
+                    status = %next.response.status(),
+                    "Structured output repair failed, returning the previous response"
+                );
+                break (body, false);
+            }
+            Err(e) => {
+                tracing::warn!(
+                    error = %e,
+                    "Structured output repair failed, returning the previous response"
+                );
+                break (body, false);
+            }
+        };
+
+        discarded.add(&body);
+        provider_name = next.provider_name;
+        model_name = next.model_name;
+        let (next_parts, next_body) = next.response.into_parts();
+        parts = next_parts;
+        bytes = read_structured_output_body(state, next_body).await?;
+    };
+
+    if discarded.apply(&mut body) {
+        bytes = serde_json::to_vec(&body).unwrap_or_default().into();
+        parts.headers.remove(http::header::CONTENT_LENGTH);
+    }
+    let result = ExecutionResult {
+        response: Response::from_parts(parts, Body::from(bytes)),
+        provider_name,
+        model_name,
+    };
+    Ok((result, Some(StructuredOutputOutcome { repairs, valid })))
+}
+
+async fn read_structured_output_body(
+    state: &AppState,
+    body: Body,
+) -> Result<axum::body::Bytes, ApiError> {
+    axum::body::to_bytes(body, state.config.server.max_response_body_bytes)
+        .await
+        .map_err(|e| {
+            tracing::warn!(error = %e, "Failed to read response body for structured output");
+            ApiError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "response_read_error",
+                "Failed to read response for structured output validation",
+            )
+        })
+}
+
+/// Hand a copy of a finished non-streaming response to shadow traffic.
+///
+/// The body is already buffered by cost injection, so reading it here only
+/// rebuilds the response; the client gets it unchanged.
+#[cfg(feature = "server")]
+#[allow(clippy::too_many_arguments)]
+async fn mirror_to_shadow(
+    state: &AppState,
+    shadow_traffic: &crate::services::shadow_traffic::ShadowTraffic,
+    selection: crate::services::shadow_traffic::ShadowSelection,
+    payload: api_types::CreateChatCompletionPayload,
+    tenant: Tenant,
+    response: Response,
+    provider: &str,
+    model: &str,
+    latency: Duration,
+) -> Response {
+    let (parts, body) = response.into_parts();
+    let bytes = match axum::body::to_bytes(body, state.config.server.max_response_body_bytes).await
+    {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to read response body for shadow traffic");
+            return Response::from_parts(parts, Body::empty());
+        }
+    };
+    let cost_microcents = parts
+        .headers
+        .get("X-Cost-Microcents")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok());
+    shadow_traffic.mirror(
+        state,
+        selection,
+        payload,
+        tenant,
+        crate::services::shadow_traffic::PrimaryResponse {
+            provider,
+            model,
+            status: parts.status.as_u16(),
+            latency_ms: latency.as_millis() as u64,
+            body: &bytes,
+            cost_microcents,
+        },
+    );
+    Response::from_parts(parts, Body::from(bytes))
+}
+
 /// Create a response
 ///
 /// Creates a model response using the Responses API format.
@@ -1391,6 +2019,30 @@ pub async fn api_v1_responses(
         .map(|Extension(ci)| (ci.ip_address, ci.user_agent))
         .unwrap_or_default();
 
+    // Fill in org/project defaults for anything the client left unset
+    if let Some(defaults) = resolve_request_defaults(&state, auth.as_ref()).await {
+        apply_responses_defaults(&mut payload, &defaults);
+    }
+
+    check_model_access(
+        &state,
+        auth.as_ref(),
+        payload
+            .model
+            .iter()
+            .chain(payload.models.iter().flatten())
+            .map(String::as_str),
+    )
+    .await?;
+
+    if payload
+        .tools
+        .as_ref()
+        .is_some_and(|tools| tools.iter().any(|t| t.is_file_search()))
+    {
+        check_entitlement(&state, auth.as_ref(), EntitledFeature::FileSearch).await?;
+    }
+
     // Route the model to a provider with dynamic support
     let model_clone = payload.model.clone();
     let models_clone = payload.models.clone();
@@ -1406,6 +2058,7 @@ pub async fn api_v1_responses(
         state.db.as_ref(),
         state.cache.as_ref(),
         state.secrets.as_ref(),
+        &state.provider_settings,
         auth.as_ref().map(|e| &e.0),
     )
     .await
@@ -1553,6 +2206,27 @@ pub async fn api_v1_responses(
     // responding. `caller_wants_streaming` preserves the caller's
     // original intent for cache/persist branching below.
     let caller_wants_streaming = payload.stream;
+    // Function tools named after one of the organization's registered
+    // HTTP tools get its stored definition, and run in the same loop as
+    // the configured ones.
+    #[cfg(feature = "server")]
+    let org_http_tools = crate::services::http_tool::resolve_org_http_tools(
+        &state,
+        &mut payload,
+        crate::services::responses_pipeline::resolve_request_org(
+            auth.as_ref().map(|e| &e.0),
+            state.default_org_id,
+        ),
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "HTTP tool lookup failed");
+        ApiError::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "http_tool_lookup_failed",
+            "Failed to load the organization's HTTP tools",
+        )
+    })?;
     #[cfg(feature = "server")]
     let payload_has_web_search = payload
         .tools
@@ -1617,8 +2291,11 @@ pub async fn api_v1_responses(
     #[cfg(all(feature = "server", not(feature = "mcp")))]
     let mcp_loops = false;
     #[cfg(feature = "server")]
+    let http_tool_loops = !org_http_tools.is_empty()
+        || crate::services::http_tool::declares_gateway_tools(&payload, &state.config.features);
+    #[cfg(feature = "server")]
     let needs_non_streaming_bridge = !caller_wants_streaming
-        && (shell_loops || web_search_loops || file_search_loops || mcp_loops);
+        && (shell_loops || web_search_loops || file_search_loops || mcp_loops || http_tool_loops);
     // WASM has no server-executed tool loop, so there is never a
     // forced-streaming bridge — requests forward to the provider as-is.
     #[cfg(not(feature = "server"))]
@@ -1721,11 +2398,20 @@ pub async fn api_v1_responses(
 
     // Check sovereignty requirements (API key + per-request)
     let sovereignty_reqs = check_sovereignty(
+        &state,
         auth.as_ref(),
         payload.sovereignty_requirements.as_ref(),
         &provider_config,
         &model_name,
-        &state.model_catalog,
+    )
+    .await?;
+
+    // Reject features the model does not support before calling the provider
+    check_capabilities(
+        &state,
+        &provider_config,
+        &model_name,
+        RequestFeatures::for_responses(&payload),
     )?;
 
     // Check if cache should be bypassed based on request headers
@@ -1823,6 +2509,28 @@ pub async fn api_v1_responses(
                 "provider": provider_name,
                 "created_at": now.timestamp(),
             });
+
+            // `stream` too: tail the event log on this connection. The
+            // run doesn't depend on the client, so a dropped connection
+            // resumes via GET with `starting_after` = the last
+            // `sequence_number` seen. The queued event carries sequence 0
+            // so it never collides with the worker's logged events.
+            if payload.stream {
+                let event = serde_json::json!({
+                    "type": "response.queued",
+                    "sequence_number": 0,
+                    "response": queued,
+                });
+                let preamble = format!("event: response.queued\ndata: {event}\n\n");
+                return super::responses_lookup::stream_response_events(
+                    state.clone(),
+                    resp_id,
+                    principal_org,
+                    None,
+                    Some(preamble.into()),
+                )
+                .await;
+            }
             return Ok(Response::builder()
                 .status(StatusCode::ACCEPTED)
                 .header("Content-Type", "application/json")
@@ -2048,6 +2756,7 @@ pub async fn api_v1_responses(
     let mut cache_status = CacheStatus::None;
 
     let cache_tenant = tenant_scope_from_auth(auth.as_ref());
+    let queue_tenant = fair_queue_tenant(&state, auth.as_ref(), &headers);
 
     // Check response cache (simple cache only for now - semantic cache not yet supported for responses)
     if let Some(ref response_cache) = state.response_cache {
@@ -2168,11 +2877,22 @@ pub async fn api_v1_responses(
         &state,
         &saved_provider_config,
         &mut payload,
+        &queue_tenant,
     )
     .await
     {
         tracing::warn!(error = %e, "Gateway compaction failed; continuing with original payload");
     }
+
+    // Reject inputs that still don't fit the model's context window
+    check_context_window(
+        &state,
+        &provider_config,
+        model_clone.as_deref().unwrap_or(&model_name),
+        &model_name,
+        &payload,
+        "input",
+    )?;
     let (response, provider_name, model_name, provider_config) = if use_concurrent_guardrails {
         let input_guardrails = state.input_guardrails.as_ref().unwrap();
         let user_id = auth
@@ -2203,6 +2923,7 @@ pub async fn api_v1_responses(
                 llm_model_name,
                 llm_payload,
                 llm_sovereignty_reqs.as_ref(),
+                &queue_tenant,
             )
             .await
         };
@@ -2272,6 +2993,7 @@ pub async fn api_v1_responses(
             model_name,
             payload.clone(),
             sovereignty_reqs.as_ref(),
+            &queue_tenant,
         )
         .await?;
         (response, provider_name, model_name, saved_provider_config)
@@ -2499,12 +3221,14 @@ pub async fn api_v1_responses(
             principal,
             mounted_skills,
             staged_input_files,
+            org_http_tools,
             containers_owner,
             container_id_hint,
             resolved_shell_env.clone(),
             req_id_str,
             final_response,
             persistence_handle,
+            None,
         )
     } else {
         final_response
@@ -2652,12 +3376,7 @@ pub async fn api_v1_responses(
     // folded the SSE transcript back to JSON, cost injection runs in
     // its blocking, body-parsing mode.
     let usage_entry = if caller_wants_streaming {
-        build_streaming_usage_entry(&auth, &state, &model_name, &provider_name, {
-            headers
-                .get("X-Hadrian-Project")
-                .and_then(|v| v.to_str().ok())
-                .and_then(|v| uuid::Uuid::parse_str(v).ok())
-        })
+        build_streaming_usage_entry(&auth, &state, &model_name, &provider_name, &headers)
     } else {
         None
     };
@@ -2841,15 +3560,24 @@ async fn apply_output_guardrails_responses(
 ))]
 #[tracing::instrument(
     name = "api.responses.compact",
-    skip(state, auth, authz, payload),
+    skip(state, headers, auth, authz, payload),
     fields(model = %payload.model)
 )]
 pub async fn api_v1_responses_compact(
     State(state): State<AppState>,
+    headers: HeaderMap,
     auth: Option<Extension<AuthenticatedRequest>>,
     authz: Option<Extension<AuthzContext>>,
     Valid(Json(mut payload)): Valid<Json<api_types::CompactRequest>>,
 ) -> Result<Response, ApiError> {
+    check_model_access(
+        &state,
+        auth.as_ref(),
+        std::iter::once(payload.model.as_str())
+            .chain(payload.models.iter().flatten().map(String::as_str)),
+    )
+    .await?;
+
     // Route + resolve the model the same way the main responses
     // handler does so per-org overrides and model-aliasing apply.
     let model_clone = payload.model.clone();
@@ -2865,6 +3593,7 @@ pub async fn api_v1_responses_compact(
         state.db.as_ref(),
         state.cache.as_ref(),
         state.secrets.as_ref(),
+        &state.provider_settings,
         auth.as_ref().map(|e| &e.0),
     )
     .await
@@ -2923,24 +3652,35 @@ pub async fn api_v1_responses_compact(
     // data-sovereignty surface as the main responses endpoint. Apply
     // the same per-API-key + per-request residency check.
     let _ = check_sovereignty(
+        &state,
         auth.as_ref(),
         payload.sovereignty_requirements.as_ref(),
         &provider_config,
         &model_name,
-        &state.model_catalog,
-    )?;
+    )
+    .await?;
 
-    CompactExecutor::execute(&state, &provider_name, &provider_config, payload)
-        .await
-        .map_err(|e| {
-            let (status, code) = match &e {
-                crate::providers::ProviderError::Unsupported(_) => {
-                    (StatusCode::NOT_IMPLEMENTED, "not_supported")
-                }
-                _ => (StatusCode::BAD_GATEWAY, "provider_error"),
-            };
-            ApiError::new(status, code, e.to_string())
-        })
+    execute_provider::<CompactExecutor>(
+        &state,
+        &provider_name,
+        &provider_config,
+        payload,
+        &fair_queue_tenant(&state, auth.as_ref(), &headers),
+    )
+    .await
+    .map_err(|e| {
+        let (status, code) = match &e {
+            crate::providers::ProviderError::Unsupported(_) => {
+                (StatusCode::NOT_IMPLEMENTED, "not_supported")
+            }
+            crate::providers::ProviderError::AtCapacity(e) => (e.status(), "provider_at_capacity"),
+            crate::providers::ProviderError::ConcurrencyLimited(_) => {
+                (StatusCode::SERVICE_UNAVAILABLE, "provider_at_capacity")
+            }
+            _ => (StatusCode::BAD_GATEWAY, "provider_error"),
+        };
+        ApiError::new(status, code, e.to_string())
+    })
 }
 
 /// Modifies the output_text in a responses API response JSON.
@@ -3005,6 +3745,22 @@ pub async fn api_v1_completions(
         .map(|Extension(ci)| (ci.ip_address, ci.user_agent))
         .unwrap_or_default();
 
+    // Fill in org/project defaults for anything the client left unset
+    if let Some(defaults) = resolve_request_defaults(&state, auth.as_ref()).await {
+        apply_completion_defaults(&mut payload, &defaults);
+    }
+
+    check_model_access(
+        &state,
+        auth.as_ref(),
+        payload
+            .model
+            .iter()
+            .chain(payload.models.iter().flatten())
+            .map(String::as_str),
+    )
+    .await?;
+
     // Route the model to a provider with dynamic support
     let model_clone = payload.model.clone();
     let models_clone = payload.models.clone();
@@ -3021,6 +3777,7 @@ pub async fn api_v1_completions(
         state.db.as_ref(),
         state.cache.as_ref(),
         state.secrets.as_ref(),
+        &state.provider_settings,
         auth.as_ref().map(|e| &e.0),
     )
     .await
@@ -3054,12 +3811,13 @@ pub async fn api_v1_completions(
 
     // Check sovereignty requirements (API key + per-request)
     let sovereignty_reqs = check_sovereignty(
+        &state,
         auth.as_ref(),
         payload.sovereignty_requirements.as_ref(),
         &provider_config,
         &model_name,
-        &state.model_catalog,
-    )?;
+    )
+    .await?;
 
     // Check if cache should be bypassed based on request headers
     let force_refresh = should_bypass_cache(&headers);
@@ -3068,6 +3826,7 @@ pub async fn api_v1_completions(
     let mut cache_status = CacheStatus::None;
 
     let cache_tenant = tenant_scope_from_auth(auth.as_ref());
+    let queue_tenant = fair_queue_tenant(&state, auth.as_ref(), &headers);
 
     // Check response cache (simple cache only - semantic cache not yet supported for completions)
     if let Some(ref response_cache) = state.response_cache {
@@ -3206,6 +3965,7 @@ pub async fn api_v1_completions(
                 llm_model_name,
                 llm_payload,
                 llm_sovereignty_reqs.as_ref(),
+                &queue_tenant,
             )
             .await
         };
@@ -3271,6 +4031,7 @@ pub async fn api_v1_completions(
             model_name,
             payload.clone(),
             sovereignty_reqs.as_ref(),
+            &queue_tenant,
         )
         .await?;
         (response, provider_name, model_name)
@@ -3383,12 +4144,7 @@ pub async fn api_v1_completions(
 
     // Create usage entry for streaming cost tracking
     let usage_entry = if is_streaming {
-        build_streaming_usage_entry(&auth, &state, &model_name, &provider_name, {
-            headers
-                .get("X-Hadrian-Project")
-                .and_then(|v| v.to_str().ok())
-                .and_then(|v| uuid::Uuid::parse_str(v).ok())
-        })
+        build_streaming_usage_entry(&auth, &state, &model_name, &provider_name, &headers)
     } else {
         None
     };
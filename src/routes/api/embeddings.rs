@@ -2,7 +2,7 @@ use axum::{Extension, Json, body::Body, extract::State, http::HeaderMap, respons
 use axum_valid::Valid;
 use http::StatusCode;
 
-use super::{ApiError, CacheStatus, check_sovereignty, should_bypass_cache};
+use super::{ApiError, CacheStatus, check_model_access, check_sovereignty, should_bypass_cache};
 use crate::{
     AppState, api_types,
     auth::AuthenticatedRequest,
@@ -10,6 +10,7 @@ use crate::{
     middleware::AuthzContext,
     routes::execution::{EmbeddingExecutor, ExecutionResult, execute_with_fallback},
     routing::{resolver, route_model_extended},
+    services::embedding_batcher::EmbeddingTarget,
 };
 
 /// Create embeddings
@@ -95,6 +96,7 @@ pub async fn api_v1_embeddings(
 ) -> Result<Response, ApiError> {
     // Route the model to a provider with dynamic support
     let model = payload.model.clone();
+    check_model_access(&state, auth.as_ref(), [model.as_str()]).await?;
     let routed = route_model_extended(Some(&model), &state.config.providers)?;
 
     // Resolve to concrete provider configuration
@@ -103,6 +105,7 @@ pub async fn api_v1_embeddings(
         state.db.as_ref(),
         state.cache.as_ref(),
         state.secrets.as_ref(),
+        &state.provider_settings,
         auth.as_ref().map(|e| &e.0),
     )
     .await
@@ -164,12 +167,13 @@ pub async fn api_v1_embeddings(
 
     // Check sovereignty requirements (API key + per-request)
     let sovereignty_reqs = check_sovereignty(
+        &state,
         auth.as_ref(),
         payload.sovereignty_requirements.as_ref(),
         &provider_config,
         &model_name,
-        &state.model_catalog,
-    )?;
+    )
+    .await?;
 
     // Check if cache should be bypassed based on request headers
     let force_refresh = should_bypass_cache(&headers);
@@ -179,10 +183,10 @@ pub async fn api_v1_embeddings(
 
     let cache_tenant = super::chat::tenant_scope_from_auth(auth.as_ref());
 
-    // Check response cache (embeddings are fully deterministic - excellent for caching)
-    if let Some(ref response_cache) = state.response_cache {
-        match response_cache
-            .lookup_embeddings(&payload, &model_name, &cache_tenant, force_refresh)
+    // Check embeddings cache (embeddings are fully deterministic - excellent for caching)
+    if let Some(ref embeddings_cache) = state.embeddings_cache {
+        match embeddings_cache
+            .lookup(&payload, &model_name, &cache_tenant, force_refresh)
             .await
         {
             CacheLookupResult::Hit(cached) => {
@@ -211,20 +215,44 @@ pub async fn api_v1_embeddings(
         }
     }
 
-    // Execute embedding with fallback support
+    // Execute embedding with fallback support, split into provider batches
+    // or coalesced with other requests when [features.embeddings] is enabled
+    let tenant = super::chat::fair_queue_tenant(&state, auth.as_ref(), &headers);
     let ExecutionResult {
         response,
         provider_name,
         model_name,
-    } = execute_with_fallback::<EmbeddingExecutor>(
-        &state,
-        provider_name,
-        provider_config,
-        model_name,
-        payload.clone(),
-        sovereignty_reqs.as_ref(),
-    )
-    .await?;
+    } = match state.embedding_batcher.clone() {
+        Some(batcher) => {
+            let target = EmbeddingTarget {
+                provider_name,
+                provider_config,
+                model_name,
+                shared: provider_source == "static",
+            };
+            batcher
+                .execute(
+                    &state,
+                    target,
+                    payload.clone(),
+                    sovereignty_reqs.as_ref(),
+                    &tenant,
+                )
+                .await?
+        }
+        None => {
+            execute_with_fallback::<EmbeddingExecutor>(
+                &state,
+                provider_name,
+                provider_config,
+                model_name,
+                payload.clone(),
+                sovereignty_reqs.as_ref(),
+                &tenant,
+            )
+            .await?
+        }
+    };
 
     // Cache successful responses
     let final_response = if cache_status == CacheStatus::Miss && response.status().is_success() {
@@ -242,9 +270,9 @@ pub async fn api_v1_embeddings(
             Ok(bytes) => {
                 let body_vec = bytes.to_vec();
 
-                // Store in response cache
-                if let Some(ref response_cache) = state.response_cache {
-                    let cache = response_cache.clone();
+                // Store in embeddings cache
+                if let Some(ref embeddings_cache) = state.embeddings_cache {
+                    let cache = embeddings_cache.clone();
                     let payload_clone = payload.clone();
                     let model_clone = model_name.clone();
                     let provider_clone = provider_name.clone();
@@ -254,7 +282,7 @@ pub async fn api_v1_embeddings(
                     #[cfg(feature = "server")]
                     state.task_tracker.spawn(async move {
                         cache
-                            .store_embeddings(
+                            .store(
                                 &payload_clone,
                                 &model_clone,
                                 &provider_clone,
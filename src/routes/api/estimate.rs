@@ -0,0 +1,340 @@
+use std::time::Duration;
+
+use axum::{Extension, Json, extract::State};
+use axum_valid::Valid;
+use http::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use validator::Validate;
+
+use super::{ApiError, TokenizeRequest, check_model_access};
+use crate::{
+    AppState,
+    auth::{ApiKeyAuth, AuthenticatedRequest},
+    cache::{Cache, CacheKeys},
+    pricing::CostPricingSource,
+    routing::{resolver, route_model_extended},
+    services::token_counter,
+};
+
+#[derive(Debug, Deserialize, Serialize, Validate)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct EstimateRequest {
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub request: TokenizeRequest,
+    /// Output cap as sent to `/v1/chat/completions` or `/v1/completions`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<i64>,
+    /// Output cap as sent to `/v1/chat/completions`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_completion_tokens: Option<i64>,
+    /// Output cap as sent to `/v1/responses`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct EstimateResponse {
+    /// Provider the model routes to
+    pub provider: String,
+    /// Model as sent to the provider
+    pub model: String,
+    /// Prompt tokens, including chat framing and tool definitions
+    pub input_tokens: u64,
+    /// `o200k_base`, `cl100k_base`, `huggingface` or `estimate`
+    pub tokenizer: String,
+    /// Whether `input_tokens` is an estimate of about 4 characters per token
+    pub estimated: bool,
+    /// The requested output cap, or the model's maximum output when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<i64>,
+    /// Cost of the prompt in microcents (1/1,000,000 of a dollar)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_cost_microcents: Option<i64>,
+    /// Cost of `max_output_tokens` in microcents
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_output_cost_microcents: Option<i64>,
+    /// Upper bound on the request's cost in microcents
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_cost_microcents: Option<i64>,
+    /// `provider_config`, `pricing_config`, `catalog` or `none`
+    pub pricing_source: String,
+    /// Rate limit and budget checks, for API key callers when a cache is
+    /// configured
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limits: Option<EstimateLimits>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct EstimateLimits {
+    /// Whether the request would pass every check
+    pub allowed: bool,
+    pub checks: Vec<LimitCheck>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct LimitCheck {
+    /// `budget_daily`, `budget_monthly`, `tokens_per_minute`,
+    /// `tokens_per_day`, `requests_per_minute` or `requests_per_day`
+    pub name: String,
+    /// Whether `current + requested` stays within `limit`
+    pub allowed: bool,
+    /// Usage so far in the window; microcents for budgets
+    pub current: i64,
+    pub limit: i64,
+    /// What the request would reserve
+    pub requested: i64,
+}
+
+/// Estimate a request's cost
+///
+/// Counts a request's prompt, prices it and its largest possible output, and
+/// checks whether it would pass the API key's budget and rate limits right
+/// now. Nothing is sent to the provider, no usage is recorded and no limits
+/// are reserved.
+///
+/// **Hadrian Extension:** This endpoint is not part of the OpenAI API specification.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/api/v1/estimate",
+    tag = "tokenize",
+    request_body(
+        content = EstimateRequest,
+        example = json!({
+            "model": "openai/gpt-4o",
+            "messages": [
+                {"role": "system", "content": "You are a helpful assistant."},
+                {"role": "user", "content": "Hello!"}
+            ],
+            "max_tokens": 1000
+        })
+    ),
+    responses(
+        (status = 200, description = "Cost estimate", body = EstimateResponse,
+            example = json!({
+                "provider": "openai",
+                "model": "gpt-4o",
+                "input_tokens": 19,
+                "tokenizer": "o200k_base",
+                "estimated": false,
+                "max_output_tokens": 1000,
+                "input_cost_microcents": 48,
+                "max_output_cost_microcents": 10000,
+                "max_cost_microcents": 10048,
+                "pricing_source": "catalog",
+                "limits": {
+                    "allowed": true,
+                    "checks": [
+                        {"name": "tokens_per_minute", "allowed": true, "current": 1200, "limit": 100000, "requested": 1019},
+                        {"name": "requests_per_minute", "allowed": true, "current": 3, "limit": 60, "requested": 1}
+                    ]
+                }
+            })
+        ),
+        (status = 400, description = "Bad request - invalid model", body = crate::openapi::ErrorResponse),
+    ),
+    security(("api_key" = []))
+))]
+#[tracing::instrument(name = "api.estimate", skip(state, auth, payload), fields(model = %payload.request.model))]
+pub async fn api_v1_estimate(
+    State(state): State<AppState>,
+    auth: Option<Extension<AuthenticatedRequest>>,
+    Valid(Json(payload)): Valid<Json<EstimateRequest>>,
+) -> Result<Json<EstimateResponse>, ApiError> {
+    let requested = payload.request.model.as_str();
+    check_model_access(&state, auth.as_ref(), [requested]).await?;
+
+    let routed = route_model_extended(Some(requested), &state.config.providers)?;
+    let resolved = resolver::resolve_to_provider(
+        routed,
+        state.db.as_ref(),
+        state.cache.as_ref(),
+        state.secrets.as_ref(),
+        &state.provider_settings,
+        auth.as_ref().map(|e| &e.0),
+    )
+    .await
+    .map_err(|e| {
+        ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "provider_resolution_error",
+            format!("Failed to resolve provider: {}", e),
+        )
+    })?;
+
+    let body = serde_json::to_value(&payload).unwrap_or(Value::Null);
+    let count = match &state.token_counter {
+        Some(counter) => counter.count_local(requested, &resolved.model, &body),
+        None => token_counter::estimate(&body),
+    };
+    let input_tokens = count.tokens as i64;
+
+    let max_output_tokens = Some(token_counter::max_output_tokens(&body))
+        .filter(|&tokens| tokens > 0)
+        .or_else(|| {
+            resolved
+                .provider_config
+                .get_model_config(&resolved.model)
+                .and_then(|config| config.max_output_tokens)
+        })
+        .or_else(|| {
+            crate::catalog::resolve_catalog_provider_id(
+                resolved.provider_config.provider_type_name(),
+                resolved.provider_config.base_url(),
+                resolved.provider_config.catalog_provider(),
+            )
+            .and_then(|id| state.model_catalog.lookup(&id, &resolved.model))
+            .and_then(|enrichment| enrichment.limits.max_output_tokens)
+        });
+
+    let input_cost =
+        state
+            .pricing
+            .calculate_cost(&resolved.provider_name, &resolved.model, input_tokens, 0);
+    let max_output_cost = max_output_tokens.and_then(|tokens| {
+        state
+            .pricing
+            .calculate_cost(&resolved.provider_name, &resolved.model, 0, tokens)
+    });
+    let pricing_source = input_cost.map_or(CostPricingSource::None, |(_, source)| source);
+    let input_cost_microcents = input_cost.map(|(cost, _)| cost);
+    let max_output_cost_microcents = max_output_cost.map(|(cost, _)| cost);
+
+    let limits = match (&state.cache, auth.as_ref().and_then(|a| a.api_key())) {
+        (Some(cache), Some(api_key)) => Some(check_limits(&state, cache, api_key, &body).await?),
+        _ => None,
+    };
+
+    Ok(Json(EstimateResponse {
+        provider: resolved.provider_name,
+        model: resolved.model,
+        input_tokens: count.tokens,
+        tokenizer: count.tokenizer.to_string(),
+        estimated: !count.is_exact(),
+        max_output_tokens,
+        input_cost_microcents,
+        max_output_cost_microcents,
+        max_cost_microcents: input_cost_microcents
+            .map(|input| input.saturating_add(max_output_cost_microcents.unwrap_or(0))),
+        pricing_source: pricing_source.as_str().to_string(),
+        limits,
+    }))
+}
+
+/// Compare what the request would reserve against the API key's current
+/// usage, without reserving anything.
+///
+/// Mirrors the reservation `api_middleware` makes: the counted prompt and
+/// requested output when token counting is used for limits, otherwise the
+/// configured per-request estimates. The per-minute request check is skipped
+/// when token buckets replace the fixed window.
+async fn check_limits(
+    state: &AppState,
+    cache: &std::sync::Arc<dyn Cache>,
+    api_key: &ApiKeyAuth,
+    body: &Value,
+) -> Result<EstimateLimits, ApiError> {
+    let rate_limits = &state.config.limits.rate_limits;
+    let reservation = state
+        .token_counter
+        .as_ref()
+        .filter(|counter| counter.use_for_limits())
+        .and_then(|counter| counter.estimate_limits(state, body));
+    let tokens = reservation.map_or(rate_limits.estimated_tokens_per_request, |e| e.tokens);
+    let cost_microcents = reservation
+        .and_then(|e| e.cost_microcents)
+        .unwrap_or_else(|| {
+            state
+                .config
+                .limits
+                .budgets
+                .estimated_cost_cents
+                .saturating_mul(10_000)
+        });
+
+    let id = api_key.key.id;
+    let mut windows = Vec::with_capacity(5);
+    if let (Some(limit_cents), Some(period)) =
+        (api_key.key.budget_limit_cents, api_key.key.budget_period)
+    {
+        windows.push((
+            format!("budget_{}", period.as_str()),
+            CacheKeys::spend(id, period),
+            CacheKeys::budget_ttl(period),
+            limit_cents.saturating_mul(10_000),
+            cost_microcents,
+        ));
+    }
+    let tpm = api_key
+        .key
+        .rate_limit_tpm
+        .map(|t| t as u32)
+        .unwrap_or(rate_limits.tokens_per_minute);
+    windows.push((
+        "tokens_per_minute".to_string(),
+        CacheKeys::rate_limit_tokens(id, "minute"),
+        Duration::from_secs(60),
+        tpm as i64,
+        tokens,
+    ));
+    if let Some(tpd) = rate_limits.tokens_per_day {
+        windows.push((
+            "tokens_per_day".to_string(),
+            CacheKeys::rate_limit_tokens(id, "day"),
+            Duration::from_secs(86400),
+            tpd as i64,
+            tokens,
+        ));
+    }
+    if state.token_buckets.is_none() {
+        let rpm = api_key
+            .key
+            .rate_limit_rpm
+            .map(|r| r as u32)
+            .unwrap_or(rate_limits.requests_per_minute);
+        windows.push((
+            "requests_per_minute".to_string(),
+            CacheKeys::rate_limit(id, "minute"),
+            Duration::from_secs(60),
+            rpm as i64,
+            1,
+        ));
+    }
+    if let Some(rpd) = rate_limits.requests_per_day {
+        windows.push((
+            "requests_per_day".to_string(),
+            CacheKeys::rate_limit(id, "day"),
+            Duration::from_secs(86400),
+            rpd as i64,
+            1,
+        ));
+    }
+
+    let mut checks = Vec::with_capacity(windows.len());
+    for (name, key, ttl, limit, requested) in windows {
+        // Adding zero reads the counter without moving it
+        let current = cache.incr_by(&key, 0, ttl).await.map_err(|e| {
+            ApiError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "cache_error",
+                format!("Failed to read limits: {}", e),
+            )
+        })?;
+        checks.push(LimitCheck {
+            name,
+            allowed: current.saturating_add(requested) <= limit,
+            current,
+            limit,
+            requested,
+        });
+    }
+
+    Ok(EstimateLimits {
+        allowed: checks.iter().all(|check| check.allowed),
+        checks,
+    })
+}
@@ -11,6 +11,8 @@ use http::StatusCode;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+#[cfg(feature = "server")]
+use super::check_entitlement;
 use super::{ApiError, SortOrder, check_resource_access_optional, get_services};
 use crate::{
     AppState,
@@ -20,6 +22,8 @@ use crate::{
     models::{File, FileId, FilePurpose, VectorStoreOwnerType},
     services::FilesService,
 };
+#[cfg(feature = "server")]
+use crate::{models::EntitledFeature, providers::ProviderError, services::provider_files};
 
 #[derive(Debug, Deserialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema, utoipa::IntoParams))]
@@ -90,6 +94,8 @@ pub struct DeleteFileResponse {
 /// - `purpose`: The intended purpose of the file (default: "assistants")
 /// - `owner_type`: Owner type - "organization", "project", or "user" (required)
 /// - `owner_id`: Owner ID (required)
+/// - `provider`: **Hadrian Extension:** Also upload the file to this configured
+///   provider, for endpoints that take the provider's file IDs (optional)
 #[cfg_attr(feature = "utoipa", utoipa::path(
     post,
     path = "/api/v1/files",
@@ -101,6 +107,8 @@ pub struct DeleteFileResponse {
         (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
         (status = 413, description = "File too large", body = crate::openapi::ErrorResponse),
         (status = 422, description = "Virus detected in uploaded file", body = crate::openapi::ErrorResponse),
+        (status = 501, description = "Provider doesn't support file uploads", body = crate::openapi::ErrorResponse),
+        (status = 502, description = "Provider error", body = crate::openapi::ErrorResponse),
     ),
     security(("api_key" = []))
 ))]
@@ -144,6 +152,7 @@ pub async fn api_v1_files_upload(
     let mut purpose = FilePurpose::Assistants;
     let mut owner_type: Option<VectorStoreOwnerType> = None;
     let mut owner_id: Option<Uuid> = None;
+    let mut provider_name: Option<String> = None;
 
     // Parse multipart form data
     while let Some(field) = multipart.next_field().await.map_err(|e| {
@@ -221,6 +230,15 @@ pub async fn api_v1_files_upload(
                     )
                 })?);
             }
+            "provider" => {
+                provider_name = Some(field.text().await.map_err(|e| {
+                    ApiError::new(
+                        StatusCode::BAD_REQUEST,
+                        "provider_read_error",
+                        format!("Failed to read provider: {}", e),
+                    )
+                })?);
+            }
             _ => {
                 // Ignore unknown fields
             }
@@ -257,6 +275,11 @@ pub async fn api_v1_files_upload(
         )
     })?;
 
+    // Batch API input files depend on the organization's plan
+    if matches!(purpose, FilePurpose::Batch) {
+        check_entitlement(&state, auth.as_ref(), EntitledFeature::BatchApi).await?;
+    }
+
     // Validate file size against configured limit
     let max_file_size = state.config.features.file_processing.max_file_size_bytes();
     let file_size = file_data.len() as i64;
@@ -401,6 +424,22 @@ pub async fn api_v1_files_upload(
         }
     }
 
+    // Resolve the provider before storing anything, so an unknown one is
+    // rejected up front
+    let provider = provider_name
+        .map(|name| {
+            let (_, provider) = provider_files::configured_provider(&state, &name)?;
+            Ok((name, provider, Bytes::from(file_data.clone())))
+        })
+        .transpose()
+        .map_err(|e: ProviderError| {
+            ApiError::new(
+                StatusCode::BAD_REQUEST,
+                "provider_resolution_error",
+                e.to_string(),
+            )
+        })?;
+
     // Create file with configured storage backend
     let storage_backend = services.files.configured_backend();
     let input = FilesService::create_file_input(
@@ -414,6 +453,21 @@ pub async fn api_v1_files_upload(
     );
 
     let file = services.files.upload(input).await?;
+
+    if let Some((name, provider, content)) = provider
+        && let Err(e) =
+            provider_files::upload(&state, db, &file, content, &name, provider.as_ref()).await
+    {
+        // Don't keep a file the client will retry uploading
+        if let Err(delete_err) = services.files.delete(file.id).await {
+            tracing::warn!(
+                error = %delete_err,
+                file_id = %file.id,
+                "Failed to delete file after provider upload failed"
+            );
+        }
+        return Err(e.into());
+    }
     Ok(Json(file))
 }
 
@@ -723,6 +777,7 @@ pub async fn api_v1_files_get_content(
 /// Delete a file
 ///
 /// Deletes a file. The file cannot be deleted if it is still referenced by any vector stores.
+/// Copies of the file uploaded to providers are deleted too.
 #[cfg_attr(feature = "utoipa", utoipa::path(
     delete,
     path = "/api/v1/files/{file_id}",
@@ -801,6 +856,12 @@ pub async fn api_v1_files_delete(
         .cleanup_soft_deleted_references(file_id)
         .await?;
 
+    // Delete provider copies first, while their mappings still exist
+    #[cfg(feature = "server")]
+    if let Some(db) = state.db.as_deref() {
+        provider_files::delete_copies(&state, db, file_id).await?;
+    }
+
     // Delete the file
     services.files.delete(file_id).await?;
 
@@ -0,0 +1,420 @@
+//! `/v1/fine_tuning/jobs/*` endpoints, proxying fine-tuning jobs to the
+//! provider the base model routes to:
+//!
+//! - `POST /v1/fine_tuning/jobs`                  — start a job
+//! - `GET  /v1/fine_tuning/jobs`                  — list the caller's jobs
+//! - `GET  /v1/fine_tuning/jobs/{job_id}`         — retrieve a job
+//! - `POST /v1/fine_tuning/jobs/{job_id}/cancel`  — cancel a job
+//!
+//! Jobs belong to the caller's organization, and to their project when they
+//! have one; callers only see jobs of their own scope. Training files
+//! uploaded to the gateway are copied to the provider the first time a job
+//! uses them (see [`crate::services::provider_files`]).
+//! See [`crate::services::fine_tuning`] for how finished jobs are billed and
+//! their models registered.
+
+#![cfg(feature = "server")]
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use axum_valid::Valid;
+use bytes::Bytes;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use super::{
+    ApiError, check_model_access, check_resource_access_optional, get_services, provider_error,
+};
+use crate::{
+    AppState,
+    api_types::{CreateFineTuningJobRequest, FineTuningJob, FineTuningJobList},
+    auth::AuthenticatedRequest,
+    db::DbPool,
+    middleware::AuthzContext,
+    models::{CreateFineTuningJobRecord, FileId, FineTuningJobRecord},
+    providers::Provider,
+    routing::{RoutedProvider, route_model_extended},
+    services::{
+        fine_tuning::{self, FineTuningError},
+        provider_files,
+    },
+};
+
+/// Query params for `GET /v1/fine_tuning/jobs`.
+#[derive(Debug, Deserialize)]
+pub struct ListFineTuningJobsQuery {
+    /// Page size. Clamped to `[1, 100]` (default 20).
+    #[serde(default)]
+    limit: Option<i64>,
+}
+
+fn get_db(state: &AppState) -> Result<&DbPool, ApiError> {
+    state.db.as_deref().ok_or_else(|| {
+        ApiError::new(
+            StatusCode::NOT_IMPLEMENTED,
+            "feature_not_available",
+            "Fine-tuning requires a configured database",
+        )
+    })
+}
+
+/// Resolve the org that owns the caller's jobs, falling back to the
+/// deployment's `default_org_id` when auth is disabled.
+fn caller_org(
+    state: &AppState,
+    auth: Option<&Extension<AuthenticatedRequest>>,
+) -> Result<Uuid, ApiError> {
+    auth.and_then(|Extension(a)| a.effective_org_id())
+        .or(state.default_org_id)
+        .ok_or_else(|| {
+            ApiError::new(
+                StatusCode::UNAUTHORIZED,
+                "authentication_required",
+                "An authenticated org is required",
+            )
+        })
+}
+
+async fn enforce_authz(
+    authz: Option<&Extension<AuthzContext>>,
+    auth: Option<&Extension<AuthenticatedRequest>>,
+    action: &str,
+) -> Result<(), ApiError> {
+    let Some(Extension(authz)) = authz else {
+        return Ok(());
+    };
+    let org_id = auth.and_then(|Extension(a)| a.effective_org_id().map(|id| id.to_string()));
+    let project_id =
+        auth.and_then(|Extension(a)| a.effective_project_id().map(|id| id.to_string()));
+    authz
+        .require_api(
+            "fine_tuning_job",
+            action,
+            None,
+            None,
+            org_id.as_deref(),
+            project_id.as_deref(),
+        )
+        .await
+        .map_err(|e| ApiError::new(StatusCode::FORBIDDEN, "authorization_denied", e.to_string()))
+}
+
+/// Load a job the caller may see. Jobs of other orgs, or of other projects
+/// when the caller has one, are reported as missing.
+async fn load_job(
+    state: &AppState,
+    db: &DbPool,
+    auth: Option<&Extension<AuthenticatedRequest>>,
+    job_id: &str,
+) -> Result<FineTuningJobRecord, ApiError> {
+    let org_id = caller_org(state, auth)?;
+    let project_id = auth.and_then(|Extension(a)| a.effective_project_id());
+    let not_found = || {
+        ApiError::new(
+            StatusCode::NOT_FOUND,
+            "not_found",
+            format!("Fine-tuning job '{job_id}' not found"),
+        )
+    };
+    let id = FineTuningJobRecord::parse_public_id(job_id).ok_or_else(not_found)?;
+    db.fine_tuning_jobs()
+        .get_by_id(id)
+        .await?
+        .filter(|job| {
+            job.org_id == org_id && (project_id.is_none() || job.project_id == project_id)
+        })
+        .ok_or_else(not_found)
+}
+
+impl From<FineTuningError> for ApiError {
+    fn from(e: FineTuningError) -> Self {
+        match e {
+            FineTuningError::Provider(e) => provider_error(e),
+            FineTuningError::Db(e) => e.into(),
+        }
+    }
+}
+
+/// The provider's ID for a training or validation file. Files uploaded to
+/// the gateway are copied to the provider the first time a job uses them;
+/// anything else is taken to be the provider's own file ID.
+async fn provider_file_id(
+    state: &AppState,
+    db: &DbPool,
+    auth: Option<&Extension<AuthenticatedRequest>>,
+    provider_name: &str,
+    provider: &dyn Provider,
+    file_id: &str,
+) -> Result<String, ApiError> {
+    let Some(id) = file_id
+        .starts_with(FileId::PREFIX)
+        .then(|| file_id.parse::<FileId>().ok())
+        .flatten()
+    else {
+        return Ok(file_id.to_string());
+    };
+    let services = get_services(state)?;
+    let Some(file) = services.files.get(id.into_inner()).await? else {
+        return Ok(file_id.to_string());
+    };
+    check_resource_access_optional(auth.map(|e| &e.0), file.owner_type, file.owner_id)?;
+
+    if let Some(copy) = db.provider_files().get(file.id, provider_name).await? {
+        return Ok(copy.provider_file_id);
+    }
+    let content = services.files.get_content(file.id).await?;
+    Ok(provider_files::upload(
+        state,
+        db,
+        &file,
+        Bytes::from(content),
+        provider_name,
+        provider,
+    )
+    .await?)
+}
+
+/// Create a fine-tuning job
+///
+/// Starts a fine-tuning job on the provider `model` routes to. Only
+/// OpenAI-compatible providers from the gateway configuration support
+/// fine-tuning.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/api/v1/fine_tuning/jobs",
+    tag = "fine-tuning",
+    request_body = CreateFineTuningJobRequest,
+    responses(
+        (status = 200, description = "The created job", body = FineTuningJob),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 401, description = "Authentication required", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Model not allowed", body = crate::openapi::ErrorResponse),
+        (status = 501, description = "Provider doesn't support fine-tuning", body = crate::openapi::ErrorResponse),
+        (status = 502, description = "Provider error", body = crate::openapi::ErrorResponse),
+    ),
+    security(("api_key" = []))
+))]
+pub async fn api_v1_fine_tuning_jobs_create(
+    State(state): State<AppState>,
+    auth: Option<Extension<AuthenticatedRequest>>,
+    authz: Option<Extension<AuthzContext>>,
+    Valid(Json(mut payload)): Valid<Json<CreateFineTuningJobRequest>>,
+) -> Result<Json<FineTuningJob>, ApiError> {
+    let db = get_db(&state)?;
+    enforce_authz(authz.as_ref(), auth.as_ref(), "write").await?;
+    let org_id = caller_org(&state, auth.as_ref())?;
+    check_model_access(&state, auth.as_ref(), [payload.model.as_str()]).await?;
+    if let Some(Extension(ref auth)) = auth
+        && let Some(api_key) = auth.api_key()
+    {
+        api_key.check_model_allowed(&payload.model).map_err(|e| {
+            ApiError::new(StatusCode::FORBIDDEN, "model_not_allowed", e.to_string())
+        })?;
+    }
+
+    let (provider_name, model) =
+        match route_model_extended(Some(payload.model.as_str()), &state.config.providers)? {
+            RoutedProvider::Static(route) => (route.provider_name.to_string(), route.model),
+            RoutedProvider::Dynamic(_) => {
+                return Err(ApiError::new(
+                    StatusCode::BAD_REQUEST,
+                    "not_supported",
+                    "Fine-tuning requires a provider from the gateway configuration",
+                ));
+            }
+        };
+    let (config, provider) =
+        provider_files::configured_provider(&state, &provider_name).map_err(|e| {
+            ApiError::new(
+                StatusCode::BAD_REQUEST,
+                "provider_resolution_error",
+                e.to_string(),
+            )
+        })?;
+
+    let training_file = payload.training_file.clone();
+    let validation_file = payload.validation_file.clone();
+    payload.training_file = provider_file_id(
+        &state,
+        db,
+        auth.as_ref(),
+        &provider_name,
+        provider.as_ref(),
+        &training_file,
+    )
+    .await?;
+    if let Some(file_id) = &validation_file {
+        payload.validation_file = Some(
+            provider_file_id(
+                &state,
+                db,
+                auth.as_ref(),
+                &provider_name,
+                provider.as_ref(),
+                file_id,
+            )
+            .await?,
+        );
+    }
+    payload.model = model.clone();
+
+    let job = provider
+        .create_fine_tuning_job(&state.http_client, payload)
+        .await
+        .map_err(provider_error)?;
+
+    let auth_request = auth.as_ref().map(|e| &e.0);
+    let api_key = auth_request.and_then(|a| a.api_key());
+    let record = db
+        .fine_tuning_jobs()
+        .create(CreateFineTuningJobRecord {
+            provider: provider_name,
+            provider_job_id: job.id.clone(),
+            org_id,
+            project_id: auth_request.and_then(AuthenticatedRequest::effective_project_id),
+            team_id: api_key.and_then(|k| k.team_id),
+            user_id: auth_request.and_then(|a| a.user_id()),
+            api_key_id: api_key.map(|k| k.key.id),
+            service_account_id: api_key.and_then(|k| k.service_account_id),
+            model,
+            training_file,
+            validation_file,
+            status: job.status.clone(),
+        })
+        .await
+        .inspect_err(|e| {
+            tracing::error!(
+                error = %e,
+                provider_job_id = %job.id,
+                "Failed to record fine-tuning job started on provider"
+            );
+        })?;
+
+    // A job can fail validation before it's first read
+    let record = fine_tuning::store_status(&state, db, &record, &config, &job).await?;
+    Ok(Json(fine_tuning::to_client_job(&record, Some(job))))
+}
+
+/// List fine-tuning jobs
+///
+/// Lists the jobs of the caller's organization, or of their project when
+/// they have one, newest first. Statuses are as of the last sync with the
+/// provider.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/api/v1/fine_tuning/jobs",
+    tag = "fine-tuning",
+    params(
+        ("limit" = Option<i64>, Query, description = "Page size, clamped to 1..=100 (default 20)"),
+    ),
+    responses(
+        (status = 200, description = "The caller's jobs", body = FineTuningJobList),
+        (status = 401, description = "Authentication required", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Authorization denied", body = crate::openapi::ErrorResponse),
+    ),
+    security(("api_key" = []))
+))]
+pub async fn api_v1_fine_tuning_jobs_list(
+    State(state): State<AppState>,
+    auth: Option<Extension<AuthenticatedRequest>>,
+    authz: Option<Extension<AuthzContext>>,
+    Query(params): Query<ListFineTuningJobsQuery>,
+) -> Result<Json<FineTuningJobList>, ApiError> {
+    let db = get_db(&state)?;
+    enforce_authz(authz.as_ref(), auth.as_ref(), "read").await?;
+    let org_id = caller_org(&state, auth.as_ref())?;
+    let project_id = auth
+        .as_ref()
+        .and_then(|Extension(a)| a.effective_project_id());
+
+    let limit = params.limit.unwrap_or(20).clamp(1, 100);
+    let mut records = db
+        .fine_tuning_jobs()
+        .list(org_id, project_id, limit + 1)
+        .await?;
+    let has_more = records.len() as i64 > limit;
+    records.truncate(limit as usize);
+
+    Ok(Json(FineTuningJobList {
+        object: "list".to_string(),
+        data: records
+            .iter()
+            .map(|record| fine_tuning::to_client_job(record, None))
+            .collect(),
+        has_more,
+    }))
+}
+
+/// Retrieve a fine-tuning job
+///
+/// Returns the job as its provider currently reports it.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/api/v1/fine_tuning/jobs/{job_id}",
+    tag = "fine-tuning",
+    params(("job_id" = String, Path, description = "Job ID (`ftjob-<uuid>`)")),
+    responses(
+        (status = 200, description = "The job", body = FineTuningJob),
+        (status = 401, description = "Authentication required", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Job not found", body = crate::openapi::ErrorResponse),
+        (status = 502, description = "Provider error", body = crate::openapi::ErrorResponse),
+    ),
+    security(("api_key" = []))
+))]
+pub async fn api_v1_fine_tuning_jobs_get(
+    State(state): State<AppState>,
+    auth: Option<Extension<AuthenticatedRequest>>,
+    authz: Option<Extension<AuthzContext>>,
+    Path(job_id): Path<String>,
+) -> Result<Json<FineTuningJob>, ApiError> {
+    let db = get_db(&state)?;
+    enforce_authz(authz.as_ref(), auth.as_ref(), "read").await?;
+    let record = load_job(&state, db, auth.as_ref(), &job_id).await?;
+
+    match fine_tuning::sync_job(&state, db, &record).await {
+        Ok((record, job)) => Ok(Json(fine_tuning::to_client_job(&record, Some(job)))),
+        // A finished job's stored state is final
+        Err(e) if record.completed_at.is_some() => {
+            tracing::debug!(error = %e, job_id = %job_id, "Serving finished fine-tuning job from storage");
+            Ok(Json(fine_tuning::to_client_job(&record, None)))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Cancel a fine-tuning job
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/api/v1/fine_tuning/jobs/{job_id}/cancel",
+    tag = "fine-tuning",
+    params(("job_id" = String, Path, description = "Job ID (`ftjob-<uuid>`)")),
+    responses(
+        (status = 200, description = "The cancelled job", body = FineTuningJob),
+        (status = 401, description = "Authentication required", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Job not found", body = crate::openapi::ErrorResponse),
+        (status = 502, description = "Provider error", body = crate::openapi::ErrorResponse),
+    ),
+    security(("api_key" = []))
+))]
+pub async fn api_v1_fine_tuning_jobs_cancel(
+    State(state): State<AppState>,
+    auth: Option<Extension<AuthenticatedRequest>>,
+    authz: Option<Extension<AuthzContext>>,
+    Path(job_id): Path<String>,
+) -> Result<Json<FineTuningJob>, ApiError> {
+    let db = get_db(&state)?;
+    enforce_authz(authz.as_ref(), auth.as_ref(), "write").await?;
+    let record = load_job(&state, db, auth.as_ref(), &job_id).await?;
+
+    let (config, provider) =
+        provider_files::configured_provider(&state, &record.provider).map_err(provider_error)?;
+    let job = provider
+        .cancel_fine_tuning_job(&state.http_client, &record.provider_job_id)
+        .await
+        .map_err(provider_error)?;
+    let record = fine_tuning::store_status(&state, db, &record, &config, &job).await?;
+    Ok(Json(fine_tuning::to_client_job(&record, Some(job))))
+}
@@ -9,7 +9,9 @@ use axum::{
 use axum_valid::Valid;
 use http::StatusCode;
 
-use super::{ApiError, check_sovereignty, image_quality_to_string, image_size_to_string};
+use super::{
+    ApiError, check_model_access, check_sovereignty, image_quality_to_string, image_size_to_string,
+};
 #[cfg(feature = "provider-azure")]
 use crate::providers::azure_openai;
 use crate::{
@@ -54,6 +56,7 @@ pub async fn api_v1_images_generations(
 ) -> Result<Response, ApiError> {
     // Route the model to a provider
     let model = payload.model.clone();
+    check_model_access(&state, auth.as_ref(), model.as_deref()).await?;
     let routed = route_model_extended(model.as_deref(), &state.config.providers)?;
 
     // Resolve to concrete provider configuration
@@ -62,6 +65,7 @@ pub async fn api_v1_images_generations(
         state.db.as_ref(),
         state.cache.as_ref(),
         state.secrets.as_ref(),
+        &state.provider_settings,
         auth.as_ref().map(|e| &e.0),
     )
     .await
@@ -134,12 +138,13 @@ pub async fn api_v1_images_generations(
 
     // Check sovereignty requirements (API key + per-request)
     let _sovereignty_reqs = check_sovereignty(
+        &state,
         auth.as_ref(),
         payload.sovereignty_requirements.as_ref(),
         &provider_config,
         &model_name,
-        &state.model_catalog,
-    )?;
+    )
+    .await?;
 
     // Replace model with resolved name (strip provider prefix like "openai/dall-e-3" → "dall-e-3")
     let mut payload = payload;
@@ -423,6 +428,7 @@ pub async fn api_v1_images_edits(
     };
 
     // Route the model to a provider
+    check_model_access(&state, auth.as_ref(), model.as_deref()).await?;
     let routed = route_model_extended(model.as_deref(), &state.config.providers)?;
 
     // Resolve to concrete provider configuration
@@ -431,6 +437,7 @@ pub async fn api_v1_images_edits(
         state.db.as_ref(),
         state.cache.as_ref(),
         state.secrets.as_ref(),
+        &state.provider_settings,
         auth.as_ref().map(|e| &e.0),
     )
     .await
@@ -731,6 +738,7 @@ pub async fn api_v1_images_variations(
     };
 
     // Route the model to a provider
+    check_model_access(&state, auth.as_ref(), model.as_deref()).await?;
     let routed = route_model_extended(model.as_deref(), &state.config.providers)?;
 
     // Resolve to concrete provider configuration
@@ -739,6 +747,7 @@ pub async fn api_v1_images_variations(
         state.db.as_ref(),
         state.cache.as_ref(),
         state.secrets.as_ref(),
+        &state.provider_settings,
         auth.as_ref().map(|e| &e.0),
     )
     .await
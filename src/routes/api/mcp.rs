@@ -0,0 +1,556 @@
+//! MCP (Model Context Protocol) server endpoint.
+//!
+//! `/v1/mcp` speaks the Streamable HTTP transport: each POST carries one
+//! JSON-RPC message and gets the reply back as JSON, or as a single-event SSE
+//! stream when the client only accepts `text/event-stream`. The server is
+//! stateless, so no `Mcp-Session-Id` is issued and GET (server-initiated
+//! streams) is not offered.
+//!
+//! Tools wrap existing `/v1` handlers and are called with the request's own
+//! auth and authz context, so RBAC, resource ownership checks and usage are
+//! identical to calling the endpoint directly. The route itself has no scope
+//! requirement; instead each tool requires the scope of the endpoint it wraps
+//! and is hidden from keys that lack it.
+
+use axum::{
+    Extension, Json,
+    body::Bytes,
+    extract::{Path, State},
+    http::HeaderMap,
+    response::{IntoResponse, Response, Sse, sse::Event},
+};
+use futures::stream;
+use http::{StatusCode, header};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use super::{ApiError, api_v1_models, api_v1_vector_stores_search, get_services};
+use crate::{
+    AppState,
+    auth::AuthenticatedRequest,
+    config::{McpServerConfig, McpServerTool},
+    db::ListParams,
+    middleware::AuthzContext,
+    models::{ApiKeyScope, RequiredScope, ScopeAccess, Template, TemplateOwnerType, VectorStoreId},
+};
+
+/// Newest protocol revision this server implements. Clients asking for an
+/// older revision we also support get that revision back.
+const PROTOCOL_VERSION: &str = "2025-06-18";
+const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
+
+/// Upper bound on templates returned by `prompts/list`.
+const MAX_PROMPTS: i64 = 100;
+
+// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcRequest {
+    jsonrpc: String,
+    /// Absent for notifications.
+    #[serde(default)]
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+/// A JSON-RPC error returned from a method.
+#[derive(Debug)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl McpServerTool {
+    fn name(self) -> &'static str {
+        match self {
+            McpServerTool::FileSearch => "file_search",
+            McpServerTool::ListModels => "list_models",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        [McpServerTool::FileSearch, McpServerTool::ListModels]
+            .into_iter()
+            .find(|tool| tool.name() == name)
+    }
+
+    /// The scope of the `/v1` endpoint the tool wraps.
+    fn required_scope(self) -> RequiredScope {
+        match self {
+            McpServerTool::FileSearch => RequiredScope::new(ApiKeyScope::Files, ScopeAccess::Write),
+            McpServerTool::ListModels => RequiredScope::new(ApiKeyScope::Models, ScopeAccess::Read),
+        }
+    }
+
+    fn definition(self) -> Value {
+        match self {
+            McpServerTool::FileSearch => json!({
+                "name": self.name(),
+                "title": "File search",
+                "description": "Semantic search over the chunks of a vector store. Returns the best matching passages with their file and score.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "vector_store_id": {
+                            "type": "string",
+                            "description": "Vector store to search (vs_...)"
+                        },
+                        "query": { "type": "string" },
+                        "max_num_results": { "type": "integer", "minimum": 1, "maximum": 50 },
+                        "filters": {
+                            "type": "object",
+                            "description": "Attribute filter, as accepted by /v1/vector_stores/{id}/search"
+                        }
+                    },
+                    "required": ["vector_store_id", "query"]
+                }
+            }),
+            McpServerTool::ListModels => json!({
+                "name": self.name(),
+                "title": "List models",
+                "description": "List the models available through this gateway, with capabilities, context limits and pricing where known.",
+                "inputSchema": { "type": "object", "properties": {} }
+            }),
+        }
+    }
+}
+
+/// Authenticated caller context, forwarded to wrapped handlers.
+struct Caller {
+    auth: Option<Extension<AuthenticatedRequest>>,
+    authz: Option<Extension<AuthzContext>>,
+}
+
+impl Caller {
+    /// Whether the caller's API key grants `scope`. Non-key identities are
+    /// not scope-restricted.
+    fn has_scope(&self, scope: &RequiredScope) -> bool {
+        self.auth
+            .as_ref()
+            .and_then(|Extension(auth)| auth.api_key())
+            .is_none_or(|key| key.key.has_scope(scope))
+    }
+
+    /// Tools visible to this caller.
+    fn tools(&self, config: &McpServerConfig) -> Vec<McpServerTool> {
+        [McpServerTool::FileSearch, McpServerTool::ListModels]
+            .into_iter()
+            .filter(|tool| config.exposes(*tool) && self.has_scope(&tool.required_scope()))
+            .collect()
+    }
+
+    /// Whether the caller can read prompt templates. Templates are chat
+    /// prompts, so they follow the `chat` scope.
+    fn can_read_prompts(&self) -> bool {
+        self.has_scope(&RequiredScope::new(ApiKeyScope::Chat, ScopeAccess::Read))
+    }
+
+    /// Template owners whose templates the caller sees: its organization and,
+    /// for project-scoped keys, its project.
+    fn template_owners(&self) -> Vec<(TemplateOwnerType, uuid::Uuid)> {
+        let Some(Extension(auth)) = &self.auth else {
+            return Vec::new();
+        };
+        let mut owners = Vec::new();
+        if let Some(key) = auth.api_key() {
+            if let Some(org_id) = key.org_id {
+                owners.push((TemplateOwnerType::Organization, org_id));
+            }
+            if let Some(project_id) = key.project_id {
+                owners.push((TemplateOwnerType::Project, project_id));
+            }
+        } else if let Some(org_id) = auth
+            .identity()
+            .and_then(|i| i.org_ids.first())
+            .and_then(|id| id.parse().ok())
+        {
+            owners.push((TemplateOwnerType::Organization, org_id));
+        }
+        owners
+    }
+}
+
+/// MCP server endpoint
+///
+/// **Hadrian Extension** - Model Context Protocol (Streamable HTTP transport).
+/// Accepts one JSON-RPC message per request.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/v1/mcp",
+    tag = "mcp",
+    request_body(content = serde_json::Value, description = "JSON-RPC 2.0 message"),
+    responses(
+        (status = 200, description = "JSON-RPC response", body = serde_json::Value),
+        (status = 202, description = "Notification accepted"),
+        (status = 404, description = "MCP server not enabled", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "api.mcp", skip_all)]
+pub async fn api_v1_mcp(
+    State(state): State<AppState>,
+    auth: Option<Extension<AuthenticatedRequest>>,
+    authz: Option<Extension<AuthzContext>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Response, ApiError> {
+    let config = state
+        .config
+        .features
+        .mcp_server
+        .clone()
+        .filter(|c| c.enabled)
+        .ok_or_else(|| {
+            ApiError::new(
+                StatusCode::NOT_FOUND,
+                "not_found",
+                "The MCP server is not enabled. Configure [features.mcp_server].",
+            )
+        })?;
+
+    let request = match parse_request(&body) {
+        Ok(request) => request,
+        Err((id, error)) => {
+            return Ok((StatusCode::BAD_REQUEST, Json(error_response(id, error))).into_response());
+        }
+    };
+
+    // Notifications (and client responses, which we never solicit) get no reply
+    let Some(id) = request.id else {
+        return Ok(StatusCode::ACCEPTED.into_response());
+    };
+
+    let caller = Caller { auth, authz };
+    let message = match dispatch(&state, &config, &caller, &request.method, request.params).await {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => error_response(id, error),
+    };
+
+    if wants_event_stream(&headers) {
+        let event = Event::default().event("message").data(message.to_string());
+        let events = stream::once(async move { Ok::<_, std::convert::Infallible>(event) });
+        return Ok(Sse::new(events).into_response());
+    }
+    Ok(Json(message).into_response())
+}
+
+fn parse_request(body: &[u8]) -> Result<JsonRpcRequest, (Value, RpcError)> {
+    let value: Value = serde_json::from_slice(body)
+        .map_err(|e| (Value::Null, RpcError::new(PARSE_ERROR, e.to_string())))?;
+    if value.is_array() {
+        return Err((
+            Value::Null,
+            RpcError::new(INVALID_REQUEST, "Batched requests are not supported"),
+        ));
+    }
+    let id = value.get("id").cloned().unwrap_or(Value::Null);
+    let request: JsonRpcRequest = serde_json::from_value(value)
+        .map_err(|e| (id.clone(), RpcError::new(INVALID_REQUEST, e.to_string())))?;
+    if request.jsonrpc != "2.0" {
+        return Err((
+            id,
+            RpcError::new(INVALID_REQUEST, "jsonrpc must be \"2.0\""),
+        ));
+    }
+    Ok(request)
+}
+
+fn error_response(id: Value, error: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": error.code, "message": error.message },
+    })
+}
+
+/// Reply over SSE only when the client can't take a plain JSON body.
+fn wants_event_stream(headers: &HeaderMap) -> bool {
+    let accept = headers
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    accept.contains("text/event-stream") && !accept.contains("application/json")
+}
+
+async fn dispatch(
+    state: &AppState,
+    config: &McpServerConfig,
+    caller: &Caller,
+    method: &str,
+    params: Value,
+) -> Result<Value, RpcError> {
+    match method {
+        "initialize" => Ok(initialize(config, &params)),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({
+            "tools": caller
+                .tools(config)
+                .into_iter()
+                .map(McpServerTool::definition)
+                .collect::<Vec<_>>(),
+        })),
+        "tools/call" => call_tool(state, config, caller, params).await,
+        "prompts/list" if config.prompts => list_prompts(state, caller).await,
+        "prompts/get" if config.prompts => get_prompt(state, caller, params).await,
+        _ => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("Method not found: {method}"),
+        )),
+    }
+}
+
+fn initialize(config: &McpServerConfig, params: &Value) -> Value {
+    let version = params
+        .get("protocolVersion")
+        .and_then(Value::as_str)
+        .filter(|v| SUPPORTED_PROTOCOL_VERSIONS.contains(v))
+        .unwrap_or(PROTOCOL_VERSION);
+
+    let mut capabilities = json!({ "tools": { "listChanged": false } });
+    if config.prompts {
+        capabilities["prompts"] = json!({ "listChanged": false });
+    }
+
+    json!({
+        "protocolVersion": version,
+        "capabilities": capabilities,
+        "serverInfo": {
+            "name": "hadrian",
+            "title": "Hadrian Gateway",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct CallToolParams {
+    name: String,
+    #[serde(default)]
+    arguments: Value,
+}
+
+async fn call_tool(
+    state: &AppState,
+    config: &McpServerConfig,
+    caller: &Caller,
+    params: Value,
+) -> Result<Value, RpcError> {
+    let params: CallToolParams =
+        serde_json::from_value(params).map_err(|e| RpcError::new(INVALID_PARAMS, e.to_string()))?;
+    let tool = McpServerTool::from_name(&params.name)
+        .filter(|tool| caller.tools(config).contains(tool))
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, format!("Unknown tool: {}", params.name)))?;
+
+    // Handler errors are reported as tool results so the model can see them
+    let result = match tool {
+        McpServerTool::FileSearch => file_search(state, caller, params.arguments).await,
+        McpServerTool::ListModels => api_v1_models(
+            State(state.clone()),
+            caller.auth.clone(),
+            caller.authz.clone(),
+        )
+        .await
+        .map(|Json(models)| json!(models)),
+    };
+
+    Ok(match result {
+        Ok(output) => json!({
+            "content": [{ "type": "text", "text": output.to_string() }],
+            "structuredContent": output,
+            "isError": false,
+        }),
+        Err(error) => json!({
+            "content": [{ "type": "text", "text": error.to_string() }],
+            "isError": true,
+        }),
+    })
+}
+
+async fn file_search(
+    state: &AppState,
+    caller: &Caller,
+    mut args: Value,
+) -> Result<Value, ApiError> {
+    let invalid =
+        |message: String| ApiError::new(StatusCode::BAD_REQUEST, "invalid_parameter", message);
+
+    let vector_store_id: VectorStoreId = args
+        .as_object_mut()
+        .and_then(|args| args.remove("vector_store_id"))
+        .and_then(|id| id.as_str().map(str::to_string))
+        .ok_or_else(|| invalid("vector_store_id is required".to_string()))?
+        .parse()
+        .map_err(|_| invalid("vector_store_id must be a vector store ID (vs_...)".to_string()))?;
+    let input = serde_json::from_value(args).map_err(|e| invalid(e.to_string()))?;
+
+    api_v1_vector_stores_search(
+        State(state.clone()),
+        caller.auth.clone(),
+        caller.authz.clone(),
+        Path(vector_store_id),
+        Json(input),
+    )
+    .await
+    .map(|Json(response)| json!(response))
+}
+
+/// Templates visible to the caller, keyed by name. Earlier owners win on
+/// name collisions, so organization templates shadow project ones.
+async fn visible_templates(state: &AppState, caller: &Caller) -> Result<Vec<Template>, RpcError> {
+    if !caller.can_read_prompts() {
+        return Ok(Vec::new());
+    }
+    let services = get_services(state).map_err(|e| RpcError::new(INTERNAL_ERROR, e.to_string()))?;
+
+    let mut templates: Vec<Template> = Vec::new();
+    for (owner_type, owner_id) in caller.template_owners() {
+        let page = services
+            .templates
+            .list_by_owner(
+                owner_type,
+                owner_id,
+                ListParams {
+                    limit: Some(MAX_PROMPTS),
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(|e| RpcError::new(INTERNAL_ERROR, e.to_string()))?;
+        for template in page.items {
+            if !templates.iter().any(|t| t.name == template.name) {
+                templates.push(template);
+            }
+        }
+    }
+    Ok(templates)
+}
+
+async fn list_prompts(state: &AppState, caller: &Caller) -> Result<Value, RpcError> {
+    let prompts: Vec<Value> = visible_templates(state, caller)
+        .await?
+        .into_iter()
+        .map(|t| json!({ "name": t.name, "description": t.description }))
+        .collect();
+    Ok(json!({ "prompts": prompts }))
+}
+
+#[derive(Debug, Deserialize)]
+struct GetPromptParams {
+    name: String,
+}
+
+async fn get_prompt(state: &AppState, caller: &Caller, params: Value) -> Result<Value, RpcError> {
+    let params: GetPromptParams =
+        serde_json::from_value(params).map_err(|e| RpcError::new(INVALID_PARAMS, e.to_string()))?;
+    let template = visible_templates(state, caller)
+        .await?
+        .into_iter()
+        .find(|t| t.name == params.name)
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, format!("Unknown prompt: {}", params.name)))?;
+
+    Ok(json!({
+        "description": template.description,
+        "messages": [{
+            "role": "user",
+            "content": { "type": "text", "text": template.content },
+        }],
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_request() {
+        let request = parse_request(br#"{"jsonrpc":"2.0","id":7,"method":"tools/list"}"#).unwrap();
+        assert_eq!(request.id, Some(json!(7)));
+        assert_eq!(request.method, "tools/list");
+
+        let notification =
+            parse_request(br#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#).unwrap();
+        assert!(notification.id.is_none());
+
+        let (id, error) = parse_request(b"{not json").unwrap_err();
+        assert_eq!(id, Value::Null);
+        assert_eq!(error.code, PARSE_ERROR);
+
+        let (id, error) =
+            parse_request(br#"{"jsonrpc":"1.0","id":"a","method":"ping"}"#).unwrap_err();
+        assert_eq!(id, json!("a"));
+        assert_eq!(error.code, INVALID_REQUEST);
+
+        let (_, error) =
+            parse_request(br#"[{"jsonrpc":"2.0","id":1,"method":"ping"}]"#).unwrap_err();
+        assert_eq!(error.code, INVALID_REQUEST);
+    }
+
+    #[test]
+    fn test_initialize_negotiates_version() {
+        let config = McpServerConfig::default();
+        let result = initialize(&config, &json!({ "protocolVersion": "2025-03-26" }));
+        assert_eq!(result["protocolVersion"], "2025-03-26");
+        assert!(result["capabilities"]["prompts"].is_object());
+
+        let result = initialize(
+            &McpServerConfig {
+                prompts: false,
+                ..Default::default()
+            },
+            &json!({ "protocolVersion": "1999-01-01" }),
+        );
+        assert_eq!(result["protocolVersion"], PROTOCOL_VERSION);
+        assert!(result["capabilities"].get("prompts").is_none());
+    }
+
+    #[test]
+    fn test_operator_tool_allowlist() {
+        let caller = Caller {
+            auth: None,
+            authz: None,
+        };
+        assert_eq!(
+            caller.tools(&McpServerConfig::default()),
+            vec![McpServerTool::FileSearch, McpServerTool::ListModels]
+        );
+
+        let config = McpServerConfig {
+            tools: Some(vec![McpServerTool::ListModels]),
+            ..Default::default()
+        };
+        assert_eq!(caller.tools(&config), vec![McpServerTool::ListModels]);
+        assert_eq!(
+            McpServerTool::from_name("list_models"),
+            Some(McpServerTool::ListModels)
+        );
+        assert_eq!(McpServerTool::from_name("shell"), None);
+    }
+
+    #[test]
+    fn test_wants_event_stream() {
+        let mut headers = HeaderMap::new();
+        assert!(!wants_event_stream(&headers));
+        headers.insert(
+            header::ACCEPT,
+            "application/json, text/event-stream".parse().unwrap(),
+        );
+        assert!(!wants_event_stream(&headers));
+        headers.insert(header::ACCEPT, "text/event-stream".parse().unwrap());
+        assert!(wants_event_stream(&headers));
+    }
+}
@@ -20,25 +20,43 @@ use crate::compat::wasm_routing::{delete, get, post};
 use crate::{
     AppState, api_types,
     auth::AuthenticatedRequest,
-    config::{ProviderConfig, SovereigntyMetadata, SovereigntyRequirements},
+    cache::{CacheExt, CacheKeys},
+    config::{DataResidencyPolicy, ProviderConfig, SovereigntyMetadata, SovereigntyRequirements},
     db::DbError,
-    models::{VectorStore, VectorStoreOwnerType},
-    routing::RoutingError,
-    services::{FilesServiceError, Services},
+    models::{
+        DegradationLadder, EntitledFeature, Entitlements, ModelAccessPolicy,
+        ModelDegradationPolicy, OrgEntitlements, RequestDefaults, VectorStore,
+        VectorStoreOwnerType,
+    },
+    routing::{RoutedProvider, RoutingError, route_model_extended},
+    services::{FilesServiceError, Services, model_degradation, token_counter},
+    validation::capabilities::{RequestFeatures, check_features},
 };
+#[cfg(feature = "server")]
+use crate::{providers::ProviderError, services::provider_files::ProviderFileError};
 
+#[cfg(feature = "server")]
+pub mod agents;
 mod audio;
 pub(crate) mod chat;
 #[cfg(feature = "server")]
 pub mod containers;
 mod embeddings;
+mod estimate;
 mod files;
+#[cfg(feature = "server")]
+pub mod fine_tuning;
 mod images;
-mod models;
+#[cfg(feature = "server")]
+pub mod mcp;
+pub(crate) mod models;
 #[cfg(feature = "server")]
 pub mod responses_lookup;
 #[cfg(feature = "server")]
 pub mod skills;
+#[cfg(feature = "server")]
+pub mod token_exchange;
+mod tokenize;
 pub(crate) mod tools;
 mod vector_stores;
 
@@ -46,9 +64,11 @@ mod vector_stores;
 pub use audio::*;
 pub use chat::*;
 pub use embeddings::*;
+pub use estimate::*;
 pub use files::*;
 pub use images::*;
 pub use models::*;
+pub use tokenize::*;
 pub use tools::*;
 pub use vector_stores::*;
 
@@ -79,21 +99,33 @@ fn should_bypass_cache(headers: &HeaderMap) -> bool {
 
 /// Enforce sovereignty requirements against the resolved provider/model.
 ///
-/// Merges API-key requirements and per-request requirements, then checks the
-/// merged result against the resolved sovereignty metadata. Returns the merged
-/// requirements on success (for use in fallback chain filtering), or an `ApiError`
-/// on violation.
-fn check_sovereignty(
+/// Merges the organization's data residency policy, API-key requirements and
+/// per-request requirements, then checks the merged result against the resolved
+/// sovereignty metadata. Returns the merged requirements on success (for use in
+/// fallback chain filtering), or an `ApiError` on violation. Violations are
+/// recorded in the audit log.
+async fn check_sovereignty(
+    state: &AppState,
     auth: Option<&Extension<AuthenticatedRequest>>,
     per_request: Option<&SovereigntyRequirements>,
     provider_config: &ProviderConfig,
     model_name: &str,
-    catalog: &crate::catalog::ModelCatalogRegistry,
 ) -> Result<Option<SovereigntyRequirements>, ApiError> {
+    let org_id = auth.and_then(|Extension(a)| a.effective_org_id());
+    let org_reqs = match org_id {
+        Some(org_id) => org_data_residency(state, org_id)
+            .await?
+            .map(|p| p.to_requirements()),
+        None => None,
+    };
+
     let key_reqs = auth
         .and_then(|Extension(a)| a.api_key())
         .and_then(|k| k.sovereignty_requirements());
-    let merged = SovereigntyRequirements::merge(key_reqs, per_request);
+    let merged = SovereigntyRequirements::merge(
+        org_reqs.as_ref(),
+        SovereigntyRequirements::merge(key_reqs, per_request).as_ref(),
+    );
     let Some(reqs) = merged else {
         return Ok(None);
     };
@@ -112,21 +144,717 @@ fn check_sovereignty(
                 provider_config.base_url(),
                 provider_config.catalog_provider(),
             )?;
-            catalog
+            state
+                .model_catalog
                 .lookup(&catalog_provider_id, model_name)
                 .map(|e| e.open_weights)
         })
         .unwrap_or(false);
 
-    reqs.check(&resolved, open_weights).map_err(|reason| {
+    if let Err(reason) = reqs.check(&resolved, open_weights) {
+        // Attribute the violation to the org policy when it alone rejects the target
+        let residency = org_reqs
+            .as_ref()
+            .is_some_and(|r| r.check(&resolved, open_weights).is_err());
+        log_sovereignty_violation(
+            state,
+            auth,
+            org_id,
+            provider_config.provider_type_name(),
+            model_name,
+            &reason,
+            residency,
+        );
+        let (code, message) = if residency {
+            (
+                "data_residency_violation",
+                format!("Request blocked by organization data residency policy: {reason}"),
+            )
+        } else {
+            (
+                "sovereignty_violation",
+                format!("Request blocked by sovereignty requirements: {reason}"),
+            )
+        };
+        return Err(ApiError::new(StatusCode::FORBIDDEN, code, message));
+    }
+
+    Ok(Some(reqs))
+}
+
+/// Reject requests using features the resolved model does not support.
+///
+/// Capabilities and modalities from the model's config override the catalog
+/// (matching the /v1/models response). Models without metadata pass.
+fn check_capabilities(
+    state: &AppState,
+    provider_config: &ProviderConfig,
+    model_name: &str,
+    features: RequestFeatures,
+) -> Result<(), ApiError> {
+    let model_config = provider_config.get_model_config(model_name);
+    let enrichment = crate::catalog::resolve_catalog_provider_id(
+        provider_config.provider_type_name(),
+        provider_config.base_url(),
+        provider_config.catalog_provider(),
+    )
+    .and_then(|id| state.model_catalog.lookup(&id, model_name));
+
+    let capabilities = model_config
+        .and_then(|mc| mc.capabilities.as_ref())
+        .or(enrichment.as_ref().map(|e| &e.capabilities));
+    let modalities = model_config
+        .and_then(|mc| mc.modalities.as_ref())
+        .or(enrichment.as_ref().map(|e| &e.modalities));
+
+    check_features(features, model_name, capabilities, modalities).map_err(|e| {
+        ApiError::new(StatusCode::BAD_REQUEST, "unsupported_parameter", e.message)
+            .with_param(e.param)
+    })
+}
+
+/// Reject prompts that don't fit the resolved model's context window.
+///
+/// The window is the model's `context_length` config, falling back to the
+/// catalog. Prompts counted with the model's tokenizer must leave room for the
+/// requested output cap; estimated counts (about 4 characters per token) are
+/// only rejected when the prompt alone overflows, leaving borderline requests
+/// to the provider.
+fn check_context_window<T: serde::Serialize>(
+    state: &AppState,
+    provider_config: &ProviderConfig,
+    requested: &str,
+    model_name: &str,
+    payload: &T,
+    param: &'static str,
+) -> Result<(), ApiError> {
+    let context_length = provider_config
+        .get_model_config(model_name)
+        .and_then(|mc| mc.context_length)
+        .or_else(|| {
+            let catalog_provider_id = crate::catalog::resolve_catalog_provider_id(
+                provider_config.provider_type_name(),
+                provider_config.base_url(),
+                provider_config.catalog_provider(),
+            )?;
+            state
+                .model_catalog
+                .lookup(&catalog_provider_id, model_name)?
+                .limits
+                .context_length
+        });
+    let Some(context_length) = context_length.filter(|&len| len > 0) else {
+        return Ok(());
+    };
+    let Ok(body) = serde_json::to_value(payload) else {
+        return Ok(());
+    };
+
+    let count = match &state.token_counter {
+        Some(counter) => counter.count_local(requested, model_name, &body),
+        None => token_counter::estimate(&body),
+    };
+    let prompt = i64::try_from(count.tokens).unwrap_or(i64::MAX);
+    if count.is_exact() {
+        let output = token_counter::max_output_tokens(&body);
+        if prompt.saturating_add(output) > context_length {
+            return Err(ApiError::new(
+                StatusCode::BAD_REQUEST,
+                "context_length_exceeded",
+                format!(
+                    "Model '{model_name}' has a context window of {context_length} tokens, but \
+                     the request needs {prompt} for the prompt and {output} for the output"
+                ),
+            )
+            .with_param(param));
+        }
+    } else if prompt > context_length {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "context_length_exceeded",
+            format!(
+                "Model '{model_name}' has a context window of {context_length} tokens, but the \
+                 prompt is about {prompt} tokens"
+            ),
+        )
+        .with_param(param));
+    }
+    Ok(())
+}
+
+/// How long an organization's data residency policy is cached for routing.
+const ORG_RESIDENCY_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Load an organization's data residency policy, consulting the cache first.
+///
+/// Lookup failures fail closed: a request must not be routed when the policy
+/// that would constrain it cannot be read.
+async fn org_data_residency(
+    state: &AppState,
+    org_id: Uuid,
+) -> Result<Option<DataResidencyPolicy>, ApiError> {
+    let Some(db) = &state.db else {
+        return Ok(None);
+    };
+
+    let cache_key = CacheKeys::org_data_residency(org_id);
+    if let Some(cache) = &state.cache
+        && let Ok(Some(policy)) = cache
+            .get_json::<Option<DataResidencyPolicy>>(&cache_key)
+            .await
+    {
+        return Ok(policy);
+    }
+
+    let policy = db
+        .organizations()
+        .get_by_id(org_id)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, %org_id, "Failed to load organization data residency policy");
+            ApiError::new(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "data_residency_unavailable",
+                "Unable to load organization data residency policy",
+            )
+        })?
+        .and_then(|org| org.data_residency);
+
+    if let Some(cache) = &state.cache {
+        let _ = cache
+            .set_json(&cache_key, &policy, ORG_RESIDENCY_CACHE_TTL)
+            .await;
+    }
+
+    Ok(policy)
+}
+
+/// Enforce the caller's organization and project model access policies.
+///
+/// Runs before routing against every model the request names (`model` and
+/// any `models` fallbacks), so a denied model is never resolved. A request
+/// must pass both policies. Denials are recorded in the audit log as
+/// `model_access.denied`.
+async fn check_model_access<'a>(
+    state: &AppState,
+    auth: Option<&Extension<AuthenticatedRequest>>,
+    models: impl IntoIterator<Item = &'a str>,
+) -> Result<(), ApiError> {
+    enforce_model_access(state, auth, models, true).await
+}
+
+/// [`check_model_access`], optionally without auditing denials, for models
+/// the gateway picks rather than the client.
+async fn enforce_model_access<'a>(
+    state: &AppState,
+    auth: Option<&Extension<AuthenticatedRequest>>,
+    models: impl IntoIterator<Item = &'a str>,
+    audit: bool,
+) -> Result<(), ApiError> {
+    let Some(Extension(auth)) = auth else {
+        return Ok(());
+    };
+    let Some(db) = &state.db else {
+        return Ok(());
+    };
+
+    let org_id = auth.effective_org_id();
+    let project_id = auth.effective_project_id();
+    let org_policy = match org_id {
+        Some(org_id) => {
+            load_model_access(state, CacheKeys::org_model_access(org_id), async {
+                db.organizations()
+                    .get_by_id(org_id)
+                    .await
+                    .map(|org| org.and_then(|o| o.model_access))
+            })
+            .await?
+        }
+        None => None,
+    };
+    let project_policy = match project_id {
+        Some(project_id) => {
+            load_model_access(state, CacheKeys::project_model_access(project_id), async {
+                db.projects()
+                    .get_by_id(project_id)
+                    .await
+                    .map(|project| project.and_then(|p| p.model_access))
+            })
+            .await?
+        }
+        None => None,
+    };
+    if org_policy.is_none() && project_policy.is_none() {
+        return Ok(());
+    }
+
+    for model in models {
+        // Unroutable models are left for routing to report
+        let provider = match route_model_extended(Some(model), &state.config.providers) {
+            Ok(RoutedProvider::Static(route)) => route.provider_name.to_string(),
+            Ok(RoutedProvider::Dynamic(route)) => route.provider_name,
+            Err(_) => continue,
+        };
+        let scopes = [
+            ("organization", org_id, org_policy.as_ref()),
+            ("project", project_id, project_policy.as_ref()),
+        ];
+        for (scope, scope_id, policy) in scopes {
+            let (Some(scope_id), Some(policy)) = (scope_id, policy) else {
+                continue;
+            };
+            if let Err(reason) = policy.check(&provider, model) {
+                if audit {
+                    log_model_access_denial(
+                        state,
+                        auth,
+                        (scope, scope_id),
+                        org_id,
+                        project_id,
+                        &provider,
+                        model,
+                    );
+                }
+                return Err(ApiError::new(
+                    StatusCode::FORBIDDEN,
+                    "model_not_allowed",
+                    format!("Request blocked by {scope} model access policy: {reason}"),
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// How long org and project model access policies are cached.
+const MODEL_ACCESS_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Load a model access policy, consulting the cache first.
+///
+/// Lookup failures fail closed: a model an admin denied must not become
+/// usable because the policy can't be read.
+async fn load_model_access(
+    state: &AppState,
+    cache_key: String,
+    load: impl std::future::Future<Output = Result<Option<ModelAccessPolicy>, DbError>>,
+) -> Result<Option<ModelAccessPolicy>, ApiError> {
+    if let Some(cache) = &state.cache
+        && let Ok(Some(policy)) = cache
+            .get_json::<Option<ModelAccessPolicy>>(&cache_key)
+            .await
+    {
+        return Ok(policy);
+    }
+
+    let policy = load.await.map_err(|e| {
+        tracing::error!(error = %e, %cache_key, "Failed to load model access policy");
         ApiError::new(
-            StatusCode::FORBIDDEN,
-            "sovereignty_violation",
-            format!("Request blocked by sovereignty requirements: {reason}"),
+            StatusCode::SERVICE_UNAVAILABLE,
+            "model_access_unavailable",
+            "Unable to load model access policy",
         )
     })?;
 
-    Ok(Some(reqs))
+    if let Some(cache) = &state.cache {
+        let _ = cache
+            .set_json(&cache_key, &policy, MODEL_ACCESS_CACHE_TTL)
+            .await;
+    }
+
+    Ok(policy)
+}
+
+/// Logs a request blocked by a model access policy to the audit log.
+///
+/// `scope` is the organization or project whose policy denied the request.
+/// Spawned in the background so the rejection is returned immediately.
+fn log_model_access_denial(
+    state: &AppState,
+    auth: &AuthenticatedRequest,
+    scope: (&'static str, Uuid),
+    org_id: Option<Uuid>,
+    project_id: Option<Uuid>,
+    provider: &str,
+    model: &str,
+) {
+    let Some(db) = &state.db else { return };
+
+    let db = db.clone();
+    let (actor_type, actor_id) = match (auth.api_key(), auth.user_id()) {
+        (Some(key), _) => (crate::models::AuditActorType::ApiKey, Some(key.key.id)),
+        (None, Some(id)) => (crate::models::AuditActorType::User, Some(id)),
+        (None, None) => (crate::models::AuditActorType::System, None),
+    };
+    let (resource_type, resource_id) = scope;
+    let details = serde_json::json!({
+        "model": model,
+        "provider": provider,
+    });
+
+    #[cfg(feature = "server")]
+    state.task_tracker.spawn(async move {
+        let result = db
+            .audit_logs()
+            .create(crate::models::CreateAuditLog {
+                actor_type,
+                actor_id,
+                action: "model_access.denied".to_string(),
+                resource_type: resource_type.to_string(),
+                resource_id,
+                org_id,
+                project_id,
+                details,
+                ip_address: None,
+                user_agent: None,
+            })
+            .await;
+
+        if let Err(e) = result {
+            tracing::warn!(error = %e, "Failed to log model access denial");
+        }
+    });
+}
+
+/// How long org and project request defaults are cached.
+const REQUEST_DEFAULTS_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Resolve the request defaults that apply to the caller.
+///
+/// Project defaults are layered over organization defaults. Unlike the data
+/// residency lookup this fails open: defaults are a convenience, so a lookup
+/// error is logged and the request proceeds exactly as the client sent it.
+async fn resolve_request_defaults(
+    state: &AppState,
+    auth: Option<&Extension<AuthenticatedRequest>>,
+) -> Option<RequestDefaults> {
+    let Extension(auth) = auth?;
+    let db = state.db.as_ref()?;
+
+    let org_defaults = match auth.effective_org_id() {
+        Some(org_id) => {
+            load_request_defaults(state, CacheKeys::org_request_defaults(org_id), async {
+                db.organizations()
+                    .get_by_id(org_id)
+                    .await
+                    .map(|org| org.and_then(|o| o.request_defaults))
+            })
+            .await
+        }
+        None => None,
+    };
+    let project_defaults = match auth.effective_project_id() {
+        Some(project_id) => {
+            load_request_defaults(
+                state,
+                CacheKeys::project_request_defaults(project_id),
+                async {
+                    db.projects()
+                        .get_by_id(project_id)
+                        .await
+                        .map(|project| project.and_then(|p| p.request_defaults))
+                },
+            )
+            .await
+        }
+        None => None,
+    };
+
+    RequestDefaults::merge(org_defaults.as_ref(), project_defaults.as_ref())
+        .filter(|d| !d.is_empty())
+}
+
+async fn load_request_defaults(
+    state: &AppState,
+    cache_key: String,
+    load: impl std::future::Future<Output = Result<Option<RequestDefaults>, DbError>>,
+) -> Option<RequestDefaults> {
+    if let Some(cache) = &state.cache
+        && let Ok(Some(defaults)) = cache.get_json::<Option<RequestDefaults>>(&cache_key).await
+    {
+        return defaults;
+    }
+
+    let defaults = match load.await {
+        Ok(defaults) => defaults,
+        Err(e) => {
+            tracing::warn!(error = %e, %cache_key, "Failed to load request defaults");
+            return None;
+        }
+    };
+
+    if let Some(cache) = &state.cache {
+        let _ = cache
+            .set_json(&cache_key, &defaults, REQUEST_DEFAULTS_CACHE_TTL)
+            .await;
+    }
+
+    defaults
+}
+
+/// How long organization model degradation policies are cached.
+const MODEL_DEGRADATION_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Resolve the caller's organization model degradation policy, if it covers
+/// the caller.
+///
+/// Fails open like request defaults: if the policy can't be read, requests
+/// go to the model the client asked for.
+async fn resolve_model_degradation(
+    state: &AppState,
+    auth: Option<&Extension<AuthenticatedRequest>>,
+) -> Option<ModelDegradationPolicy> {
+    let Extension(auth) = auth?;
+    let db = state.db.as_ref()?;
+    let org_id = auth.effective_org_id()?;
+    let cache_key = CacheKeys::org_model_degradation(org_id);
+
+    let cached = match &state.cache {
+        Some(cache) => cache
+            .get_json::<Option<ModelDegradationPolicy>>(&cache_key)
+            .await
+            .ok()
+            .flatten(),
+        None => None,
+    };
+    let policy = match cached {
+        Some(policy) => policy,
+        None => {
+            let policy = match db.organizations().get_by_id(org_id).await {
+                Ok(org) => org.and_then(|o| o.model_degradation),
+                Err(e) => {
+                    tracing::warn!(error = %e, %cache_key, "Failed to load model degradation policy");
+                    return None;
+                }
+            };
+            if let Some(cache) = &state.cache {
+                let _ = cache
+                    .set_json(&cache_key, &policy, MODEL_DEGRADATION_CACHE_TTL)
+                    .await;
+            }
+            policy
+        }
+    };
+
+    policy.filter(|p| p.applies_to(auth.api_key().map(|k| k.key.id)))
+}
+
+/// How long organization entitlements are cached.
+const ENTITLEMENTS_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Resolve the caller's organization entitlements.
+///
+/// `None` when `[features.entitlements]` is disabled or the request can't be
+/// attributed to an organization, in which case nothing is limited. Lookup
+/// failures fail closed like model access policies.
+async fn resolve_entitlements(
+    state: &AppState,
+    auth: Option<&Extension<AuthenticatedRequest>>,
+) -> Result<Option<Entitlements>, ApiError> {
+    let config = &state.config.features.entitlements;
+    if !config.enabled {
+        return Ok(None);
+    }
+    let (Some(Extension(auth)), Some(db)) = (auth, &state.db) else {
+        return Ok(None);
+    };
+    let Some(org_id) = auth.effective_org_id() else {
+        return Ok(None);
+    };
+    let cache_key = CacheKeys::org_entitlements(org_id);
+
+    if let Some(cache) = &state.cache
+        && let Ok(Some(assigned)) = cache.get_json::<Option<OrgEntitlements>>(&cache_key).await
+    {
+        return Ok(Some(Entitlements::resolve(config, assigned.as_ref())));
+    }
+
+    let assigned = db
+        .organizations()
+        .get_by_id(org_id)
+        .await
+        .map(|org| org.and_then(|o| o.entitlements))
+        .map_err(|e| {
+            tracing::error!(error = %e, %cache_key, "Failed to load organization entitlements");
+            ApiError::new(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "entitlements_unavailable",
+                "Unable to load organization entitlements",
+            )
+        })?;
+
+    if let Some(cache) = &state.cache {
+        let _ = cache
+            .set_json(&cache_key, &assigned, ENTITLEMENTS_CACHE_TTL)
+            .await;
+    }
+
+    Ok(Some(Entitlements::resolve(config, assigned.as_ref())))
+}
+
+/// Reject the request unless the caller's organization is entitled to
+/// `feature`.
+async fn check_entitlement(
+    state: &AppState,
+    auth: Option<&Extension<AuthenticatedRequest>>,
+    feature: EntitledFeature,
+) -> Result<(), ApiError> {
+    let Some(entitlements) = resolve_entitlements(state, auth).await? else {
+        return Ok(());
+    };
+    entitlements.check(feature).map_err(|reason| {
+        tracing::debug!(feature = feature.as_str(), plan = %entitlements.plan, "Feature not entitled");
+        ApiError::new(StatusCode::FORBIDDEN, "feature_not_entitled", reason)
+    })
+}
+
+/// Whether the caller's API key, if any, may use `model`.
+fn api_key_allows(auth: Option<&Extension<AuthenticatedRequest>>, model: &str) -> bool {
+    auth.and_then(|Extension(a)| a.api_key())
+        .is_none_or(|api_key| api_key.check_model_allowed(model).is_ok())
+}
+
+/// The first rung of a degradation ladder at or after `start` that the
+/// caller may use and whose provider's circuit breaker isn't open.
+async fn available_rung(
+    state: &AppState,
+    auth: Option<&Extension<AuthenticatedRequest>>,
+    ladder: &DegradationLadder,
+    start: usize,
+) -> Option<(usize, String)> {
+    for (rung, model) in ladder.downgrades.iter().enumerate().skip(start) {
+        if model_degradation::circuit_open(state, model)
+            || !api_key_allows(auth, model)
+            || enforce_model_access(state, auth, [model.as_str()], false)
+                .await
+                .is_err()
+        {
+            continue;
+        }
+        return Some((rung, model.clone()));
+    }
+    None
+}
+
+/// Fill in chat completion fields the client omitted from the resolved defaults.
+///
+/// The default system prompt is only prepended when the conversation has no
+/// system or developer message of its own.
+fn apply_chat_defaults(
+    payload: &mut api_types::CreateChatCompletionPayload,
+    defaults: &RequestDefaults,
+) {
+    if payload.model.is_none() && payload.models.is_none() {
+        payload.model.clone_from(&defaults.model);
+    }
+    if payload.temperature.is_none() {
+        payload.temperature = defaults.temperature;
+    }
+    if let Some(prompt) = &defaults.system_prompt
+        && !payload.messages.iter().any(|m| {
+            matches!(
+                m,
+                api_types::Message::System { .. } | api_types::Message::Developer { .. }
+            )
+        })
+    {
+        payload.messages.insert(
+            0,
+            api_types::Message::System {
+                content: api_types::MessageContent::Text(prompt.clone()),
+                name: None,
+            },
+        );
+    }
+}
+
+/// Fill in Responses API fields the client omitted from the resolved defaults.
+fn apply_responses_defaults(
+    payload: &mut api_types::CreateResponsesPayload,
+    defaults: &RequestDefaults,
+) {
+    if payload.model.is_none() && payload.models.is_none() {
+        payload.model.clone_from(&defaults.model);
+    }
+    if payload.temperature.is_none() {
+        payload.temperature = defaults.temperature;
+    }
+    if payload.instructions.is_none() {
+        payload.instructions.clone_from(&defaults.system_prompt);
+    }
+}
+
+/// Fill in legacy completion fields the client omitted from the resolved
+/// defaults. Completions have no system prompt, so only model and temperature
+/// apply.
+fn apply_completion_defaults(
+    payload: &mut api_types::CreateCompletionPayload,
+    defaults: &RequestDefaults,
+) {
+    if payload.model.is_none() && payload.models.is_none() {
+        payload.model.clone_from(&defaults.model);
+    }
+    if payload.temperature.is_none() {
+        payload.temperature = defaults.temperature;
+    }
+}
+
+/// Logs a blocked sovereignty / data residency check to the audit log.
+///
+/// Spawned in the background so the rejection is returned immediately.
+fn log_sovereignty_violation(
+    state: &AppState,
+    auth: Option<&Extension<AuthenticatedRequest>>,
+    org_id: Option<Uuid>,
+    provider_type: &str,
+    model: &str,
+    reason: &str,
+    residency: bool,
+) {
+    let Some(db) = &state.db else { return };
+
+    let db = db.clone();
+    let api_key_id = auth.and_then(|a| a.0.api_key().map(|k| k.key.id));
+    let user_id = auth.and_then(|a| a.0.user_id());
+    let project_id = auth.and_then(|a| a.0.api_key().and_then(|k| k.project_id));
+    let action = if residency {
+        "data_residency.violation"
+    } else {
+        "sovereignty.violation"
+    };
+    let details = serde_json::json!({
+        "provider_type": provider_type,
+        "model": model,
+        "reason": reason,
+    });
+
+    #[cfg(feature = "server")]
+    state.task_tracker.spawn(async move {
+        let (actor_type, actor_id) = match (api_key_id, user_id) {
+            (Some(id), _) => (crate::models::AuditActorType::ApiKey, Some(id)),
+            (None, Some(id)) => (crate::models::AuditActorType::User, Some(id)),
+            (None, None) => (crate::models::AuditActorType::System, None),
+        };
+        let result = db
+            .audit_logs()
+            .create(crate::models::CreateAuditLog {
+                actor_type,
+                actor_id,
+                action: action.to_string(),
+                resource_type: "sovereignty".to_string(),
+                resource_id: org_id.unwrap_or(Uuid::nil()),
+                org_id,
+                project_id,
+                details,
+                ip_address: None,
+                user_agent: None,
+            })
+            .await;
+
+        if let Err(e) = result {
+            tracing::warn!(error = %e, action, "Failed to log sovereignty audit event");
+        }
+    });
 }
 
 /// Check if any messages contain image content (multimodal).
@@ -243,11 +971,12 @@ fn voice_to_string(voice: &api_types::Voice) -> &'static str {
 }
 
 /// Error response for API requests.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ApiError {
     status: StatusCode,
     code: &'static str,
     message: String,
+    param: Option<&'static str>,
 }
 
 impl ApiError {
@@ -257,8 +986,15 @@ impl ApiError {
             status,
             code,
             message: message.into(),
+            param: None,
         }
     }
+
+    /// Name the request parameter that caused the error
+    pub fn with_param(mut self, param: &'static str) -> Self {
+        self.param = Some(param);
+        self
+    }
 }
 
 impl std::fmt::Display for ApiError {
@@ -269,7 +1005,12 @@ impl std::fmt::Display for ApiError {
 
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
-        let body = crate::openapi::ErrorResponse::new(self.code, self.message);
+        let body = match self.param {
+            Some(param) => {
+                crate::openapi::ErrorResponse::with_param(self.code, self.message, param)
+            }
+            None => crate::openapi::ErrorResponse::new(self.code, self.message),
+        };
         (self.status, Json(body)).into_response()
     }
 }
@@ -326,6 +1067,27 @@ impl From<FilesServiceError> for ApiError {
     }
 }
 
+/// Error for endpoints that manage resources at a provider: operations the
+/// provider doesn't support are a 501, and anything else a 502.
+#[cfg(feature = "server")]
+fn provider_error(e: ProviderError) -> ApiError {
+    let (status, code) = match &e {
+        ProviderError::Unsupported(_) => (StatusCode::NOT_IMPLEMENTED, "not_supported"),
+        _ => (StatusCode::BAD_GATEWAY, "provider_error"),
+    };
+    ApiError::new(status, code, e.to_string())
+}
+
+#[cfg(feature = "server")]
+impl From<ProviderFileError> for ApiError {
+    fn from(err: ProviderFileError) -> Self {
+        match err {
+            ProviderFileError::Provider(e) => provider_error(e),
+            ProviderFileError::Db(e) => e.into(),
+        }
+    }
+}
+
 /// Sort order for list queries.
 ///
 /// OpenAI-compatible sort order parameter for paginated list endpoints.
@@ -864,6 +1626,9 @@ pub(crate) fn api_v1_routes(limits: ApiBodyLimits) -> Router<AppState> {
         .route("/v1/completions", post(api_v1_completions))
         .route("/v1/embeddings", post(api_v1_embeddings))
         .route("/v1/models", get(api_v1_models))
+        // Token counting and cost estimates (Hadrian extension)
+        .route("/v1/tokenize", post(api_v1_tokenize))
+        .route("/v1/estimate", post(api_v1_estimate))
         // Images API (OpenAI-compatible)
         .route("/v1/images/generations", post(api_v1_images_generations))
         // Tools API (Hadrian extension)
@@ -905,8 +1670,33 @@ pub(crate) fn api_v1_routes(limits: ApiBodyLimits) -> Router<AppState> {
             "/v1/containers/{container_id}/files/{file_id}/content",
             get(containers::api_v1_containers_file_content),
         )
+        .route(
+            "/v1/agents/{agent_id}/runs",
+            post(agents::api_v1_agent_runs_create).get(agents::api_v1_agent_runs_list),
+        )
+        .route(
+            "/v1/agents/{agent_id}/runs/{run_id}",
+            get(agents::api_v1_agent_runs_get),
+        )
+        .route(
+            "/v1/fine_tuning/jobs",
+            post(fine_tuning::api_v1_fine_tuning_jobs_create)
+                .get(fine_tuning::api_v1_fine_tuning_jobs_list),
+        )
+        .route(
+            "/v1/fine_tuning/jobs/{job_id}",
+            get(fine_tuning::api_v1_fine_tuning_jobs_get),
+        )
+        .route(
+            "/v1/fine_tuning/jobs/{job_id}/cancel",
+            post(fine_tuning::api_v1_fine_tuning_jobs_cancel),
+        )
         .route("/v1/images/edits", post(api_v1_images_edits))
-        .route("/v1/images/variations", post(api_v1_images_variations));
+        .route("/v1/images/variations", post(api_v1_images_variations))
+        // MCP server (Hadrian extension)
+        .route("/v1/mcp", post(mcp::api_v1_mcp))
+        // API key exchange for short-lived tokens (Hadrian extension)
+        .route("/v1/auth/token", post(token_exchange::api_v1_auth_token));
     let router = router
         // Audio API (OpenAI-compatible). speech is text-only (small payload), so
         // it stays on the global limit; transcription/translation receive raw
@@ -993,6 +1783,10 @@ pub(crate) fn api_v1_routes(limits: ApiBodyLimits) -> Router<AppState> {
             "/v1/vector_stores/{vector_store_id}/files/{file_id}",
             get(api_v1_vector_stores_get_file).merge(delete(api_v1_vector_stores_delete_file)),
         )
+        .route(
+            "/v1/vector_stores/{vector_store_id}/files/{file_id}/content",
+            get(api_v1_vector_stores_get_file_content),
+        )
         // Hadrian extension: chunk inspection (not in OpenAI API)
         .route(
             "/v1/vector_stores/{vector_store_id}/files/{file_id}/chunks",
@@ -1029,11 +1823,21 @@ pub fn get_api_routes(state: AppState) -> Router<AppState> {
     };
     api_v1_routes(limits)
         // Apply middleware layers in order (ServiceBuilder runs top-to-bottom):
+        // 0. Draining - turn away new requests, track in-flight ones
         // 1. Rate limiting - reject requests early before auth overhead
         // 2. Auth, budget, usage - authenticates and sets AuthenticatedRequest
         // 3. Authorization - policy checks (needs AuthenticatedRequest from step 2)
+        // 4. Idempotency - replay responses for repeated Idempotency-Keys
+        // 5. Request policies - per-org CEL allow/deny/modify rules
+        // 6. Parameter governance - per-org/project parameter bounds
+        // 7. Provenance - sign generated text per [features.provenance]
+        // 8. Transforms - rewrite bodies/headers per [features.transforms]
         .route_layer(
             ServiceBuilder::new()
+                .layer(from_fn_with_state(
+                    state.clone(),
+                    crate::middleware::drain_middleware,
+                ))
                 .layer(from_fn_with_state(
                     state.clone(),
                     crate::middleware::rate_limit_middleware,
@@ -1043,8 +1847,28 @@ pub fn get_api_routes(state: AppState) -> Router<AppState> {
                     crate::middleware::api_middleware,
                 ))
                 .layer(from_fn_with_state(
-                    state,
+                    state.clone(),
                     crate::middleware::api_authz_middleware,
+                ))
+                .layer(from_fn_with_state(
+                    state.clone(),
+                    crate::middleware::idempotency_middleware,
+                ))
+                .layer(from_fn_with_state(
+                    state.clone(),
+                    crate::middleware::request_policies_middleware,
+                ))
+                .layer(from_fn_with_state(
+                    state.clone(),
+                    crate::middleware::parameter_governance_middleware,
+                ))
+                .layer(from_fn_with_state(
+                    state.clone(),
+                    crate::middleware::provenance_middleware,
+                ))
+                .layer(from_fn_with_state(
+                    state,
+                    crate::middleware::transforms_middleware,
                 )),
         )
 }
@@ -1240,6 +2064,24 @@ model_name = "test-model"
         (status, headers, body.to_vec())
     }
 
+    /// Helper to make a JSON PUT request
+    async fn put_json(app: &axum::Router, uri: &str, body: Value) -> (StatusCode, Value) {
+        let request = Request::builder()
+            .method("PUT")
+            .uri(uri)
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_string(&body).unwrap()))
+            .unwrap();
+
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+        (status, json)
+    }
+
     /// Helper to make a DELETE request
     async fn delete_json(app: &axum::Router, uri: &str) -> (StatusCode, Value) {
         let request = Request::builder()
@@ -1305,6 +2147,114 @@ model_name = "test-model"
         assert_eq!(usage["total_tokens"], 20);
     }
 
+    #[tokio::test]
+    async fn test_chat_completions_provenance() {
+        use crate::services::provenance::{PROVENANCE_HEADER, ProvenanceMetadata};
+
+        let key = "0123456789abcdef0123456789abcdef";
+        let app = test_app_with_auth(&format!(
+            r#"
+[auth.session]
+secret = "test-session-secret-must-be-long-enough-for-hmac-pepper-32b"
+
+[features.provenance]
+enabled = true
+signing_key = "{key}"
+delivery = "both"
+"#
+        ))
+        .await;
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/v1/chat/completions")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                json!({
+                    "model": "test/test-model",
+                    "messages": [{"role": "user", "content": "Hello"}]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let header = response
+            .headers()
+            .get(PROVENANCE_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(ProvenanceMetadata::from_header)
+            .expect("provenance header");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+
+        let metadata: ProvenanceMetadata =
+            serde_json::from_value(body["provenance"].clone()).expect("provenance field");
+        assert_eq!(metadata, header);
+        assert!(metadata.model.ends_with("test-model"));
+        let text = body["choices"][0]["message"]["content"].as_str().unwrap();
+        assert_eq!(metadata.verify(key, text), Ok(()));
+    }
+
+    #[tokio::test]
+    async fn test_chat_completions_post_processing() {
+        let app = test_app_with_auth(
+            r#"
+[auth.session]
+secret = "test-session-secret-must-be-long-enough-for-hmac-pepper-32b"
+
+[features.transforms]
+[[features.transforms.rules]]
+type = "redact"
+patterns = ["test provider"]
+replacement = "[vendor]"
+"#,
+        )
+        .await;
+
+        let (status, body) = post_json_raw(
+            &app,
+            "/api/v1/chat/completions",
+            json!({
+                "model": "test/test-model",
+                "messages": [{"role": "user", "content": "Hello"}]
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let body: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(
+            body["choices"][0]["message"]["content"],
+            "This is a test response from the [vendor]."
+        );
+
+        let (status, body) = post_json_raw(
+            &app,
+            "/api/v1/chat/completions",
+            json!({
+                "model": "test/test-model",
+                "messages": [{"role": "user", "content": "Hello"}],
+                "stream": true
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.ends_with("[DONE]\n\n"));
+        let streamed: String = body
+            .lines()
+            .filter_map(|line| line.strip_prefix("data: "))
+            .filter_map(|data| serde_json::from_str::<Value>(data).ok())
+            .filter_map(|chunk| {
+                chunk["choices"][0]["delta"]["content"]
+                    .as_str()
+                    .map(String::from)
+            })
+            .collect();
+        assert_eq!(streamed, "This is a test response from the [vendor].");
+    }
+
     #[tokio::test]
     async fn test_chat_completions_streaming_content_validation() {
         let app = test_app().await;
@@ -1801,6 +2751,65 @@ model_name = "test-model"
         assert_eq!(body["object"], "list");
     }
 
+    #[tokio::test]
+    async fn test_embeddings_batching() {
+        let app = test_app_with_auth(
+            r#"
+[auth.session]
+secret = "test-session-secret-must-be-long-enough-for-hmac-pepper-32b"
+
+[features.embeddings]
+enabled = true
+batch_sizes = { "test" = 2 }
+
+[features.embeddings.coalesce]
+window_ms = 200
+"#,
+        )
+        .await;
+
+        // Five inputs go out as three batches and come back in order
+        let inputs = ["one", "two", "three", "four", "five"];
+        let (status, body) = post_json(
+            &app,
+            "/api/v1/embeddings",
+            json!({"model": "test/test-model", "input": inputs, "dimensions": 8}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let data = body["data"].as_array().unwrap();
+        assert_eq!(data.len(), 5);
+        for (i, item) in data.iter().enumerate() {
+            assert_eq!(item["index"], i);
+        }
+        let (_, single) = post_json(
+            &app,
+            "/api/v1/embeddings",
+            json!({"model": "test/test-model", "input": "five", "dimensions": 8}),
+        )
+        .await;
+        assert_eq!(data[4]["embedding"], single["data"][0]["embedding"]);
+        assert_eq!(body["usage"]["prompt_tokens"], 24);
+
+        // Concurrent single inputs share one provider request and its usage
+        let request = |text: &str| {
+            post_json(
+                &app,
+                "/api/v1/embeddings",
+                json!({"model": "test/test-model", "input": text, "dimensions": 8}),
+            )
+        };
+        let ((status_a, a), (status_b, b)) = tokio::join!(request("Hello"), request("World"));
+        assert_eq!(status_a, StatusCode::OK);
+        assert_eq!(status_b, StatusCode::OK);
+        assert_eq!(a["data"].as_array().unwrap().len(), 1);
+        assert_eq!(a["data"][0]["index"], 0);
+        assert_eq!(b["data"][0]["index"], 0);
+        assert_ne!(a["data"][0]["embedding"], b["data"][0]["embedding"]);
+        assert_eq!(a["usage"]["prompt_tokens"], 4);
+        assert_eq!(b["usage"]["prompt_tokens"], 4);
+    }
+
     #[tokio::test]
     async fn test_embeddings_missing_input_error() {
         let app = test_app().await;
@@ -1989,6 +2998,286 @@ model_name = "test-model"
         assert_eq!(body["object"], "chat.completion");
     }
 
+    #[tokio::test]
+    async fn test_model_access_policy() {
+        let app = test_app().await;
+
+        let (_, org) = post_json(
+            &app,
+            "/admin/v1/organizations",
+            json!({"slug": "model-access-org", "name": "Model Access"}),
+        )
+        .await;
+        let org_id = org["id"].as_str().unwrap();
+        let (_, api_key_response) = post_json(
+            &app,
+            "/admin/v1/api-keys",
+            json!({"name": "model-access-key", "owner": {"type": "organization", "org_id": org_id}}),
+        )
+        .await;
+        let api_key = api_key_response["key"].as_str().unwrap();
+
+        let (status, _) = put_json(
+            &app,
+            "/admin/v1/organizations/model-access-org/model-access",
+            json!({
+                "allowed_models": ["test-model*", "secondary-model"],
+                "denied_providers": ["secondary-test"],
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        let chat = |model: &str| json!({"model": model, "messages": [{"role": "user", "content": "Hello"}]});
+        let auth = format!("Bearer {}", api_key);
+
+        let (status, _) = post_json_with_headers(
+            &app,
+            "/api/v1/chat/completions",
+            chat("test/test-model"),
+            vec![("Authorization", &auth)],
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        // Denied provider, even though the model is allowed
+        let (status, body) = post_json_with_headers(
+            &app,
+            "/api/v1/chat/completions",
+            chat("secondary-test/secondary-model"),
+            vec![("Authorization", &auth)],
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"]["code"], "model_not_allowed");
+
+        // Not on the allowlist
+        let (status, _) = post_json_with_headers(
+            &app,
+            "/api/v1/chat/completions",
+            chat("test/other-model"),
+            vec![("Authorization", &auth)],
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        let (status, body) = get_json(
+            &app,
+            "/admin/v1/organizations/model-access-org/allowed-models",
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let ids: Vec<&str> = body["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["id"].as_str().unwrap())
+            .collect();
+        assert!(ids.contains(&"test/test-model"), "{ids:?}");
+        assert!(ids.iter().all(|id| id.starts_with("test/")), "{ids:?}");
+
+        let (status, _) = delete_json(
+            &app,
+            "/admin/v1/organizations/model-access-org/model-access",
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = post_json_with_headers(
+            &app,
+            "/api/v1/chat/completions",
+            chat("secondary-test/secondary-model"),
+            vec![("Authorization", &auth)],
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_model_access_policy_applies_to_client_cert() {
+        let app = test_app().await;
+
+        let (status, _) = post_json(
+            &app,
+            "/admin/v1/organizations",
+            json!({"slug": "mtls-model-org", "name": "mTLS Model Access"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let (status, _) = post_json(
+            &app,
+            "/admin/v1/organizations/mtls-model-org/service-accounts",
+            json!({"slug": "ingest", "name": "Ingest"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let fingerprint = "ab".repeat(32);
+        let (status, _) = post_json(
+            &app,
+            "/admin/v1/organizations/mtls-model-org/service-accounts/ingest/client-certs",
+            json!({"match_type": "fingerprint_sha256", "match_value": fingerprint}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let (status, _) = put_json(
+            &app,
+            "/admin/v1/organizations/mtls-model-org/model-access",
+            json!({"denied_models": ["secondary-model"]}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        let chat = |model: &str| {
+            let cert = crate::auth::ClientCertificate {
+                subject_cn: None,
+                san_dns: vec![],
+                san_uris: vec![],
+                san_emails: vec![],
+                fingerprint_sha256: fingerprint.clone(),
+            };
+            let mut request = Request::builder()
+                .method("POST")
+                .uri("/api/v1/chat/completions")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    json!({"model": model, "messages": [{"role": "user", "content": "Hello"}]})
+                        .to_string(),
+                ))
+                .unwrap();
+            request.extensions_mut().insert(std::sync::Arc::new(cert));
+            app.clone().oneshot(request)
+        };
+
+        let response = chat("test/test-model").await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // The service account's org denylist applies without an API key
+        let response = chat("secondary-test/secondary-model").await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_org_policies_apply_to_bearer_jwt() {
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        use axum::Extension;
+
+        use crate::{
+            auth::{AuthenticatedRequest, BearerJwtAuth, IdentityKind},
+            models::{CreateOrganization, EntitledFeature, UpdateOrganization},
+        };
+
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let db_id = COUNTER.fetch_add(1, Ordering::SeqCst);
+        let config = crate::config::GatewayConfig::parse(&format!(
+            r#"
+[database]
+type = "sqlite"
+path = "file:api_test_bearer_jwt_policies_db_{db_id}?mode=memory&cache=shared"
+create_if_missing = true
+run_migrations = true
+wal_mode = false
+busy_timeout_ms = 5000
+
+[providers]
+default_provider = "test"
+
+[providers.test]
+type = "test"
+model_name = "test-model"
+
+[providers.test.sovereignty]
+region = "us"
+
+[features.entitlements]
+enabled = true
+"#
+        ))
+        .expect("Failed to parse test config");
+        let state = crate::AppState::new(config.clone())
+            .await
+            .expect("Failed to create AppState");
+        let orgs = state.db.as_ref().unwrap().organizations();
+
+        let org = orgs
+            .create(CreateOrganization {
+                slug: "jwt-policies".to_string(),
+                name: "JWT Policies".to_string(),
+            })
+            .await
+            .unwrap();
+        orgs.update(
+            org.id,
+            UpdateOrganization {
+                name: None,
+                data_residency: Some(Some(
+                    serde_json::from_value(json!({"allowed_regions": ["eu"]})).unwrap(),
+                )),
+                request_defaults: None,
+                conversation_summaries: None,
+            },
+        )
+        .await
+        .unwrap();
+        orgs.set_model_access(
+            org.id,
+            Some(&serde_json::from_value(json!({"denied_models": ["other-model"]})).unwrap()),
+        )
+        .await
+        .unwrap();
+        orgs.set_model_degradation(
+            org.id,
+            Some(
+                &serde_json::from_value(json!({
+                    "ladders": [{"model": "test/test-model", "downgrades": ["test/small-model"]}],
+                }))
+                .unwrap(),
+            ),
+        )
+        .await
+        .unwrap();
+
+        let auth = Extension(AuthenticatedRequest::new(IdentityKind::BearerJwt(
+            Box::new(BearerJwtAuth {
+                issuer: "https://auth.example.com/".to_string(),
+                subject: "reporting-job".to_string(),
+                user_id: None,
+                org_id: Some(org.id),
+                project_id: None,
+                roles: vec![],
+                email: None,
+                name: None,
+            }),
+        )));
+        let auth = Some(&auth);
+
+        let provider_config = config.providers.get("test").unwrap();
+        assert!(
+            super::check_sovereignty(&state, auth, None, provider_config, "test-model")
+                .await
+                .is_err(),
+            "org data residency must apply to JWT callers"
+        );
+        assert!(
+            super::check_model_access(&state, auth, ["test/other-model"])
+                .await
+                .is_err(),
+            "org model denylist must apply to JWT callers"
+        );
+        assert!(
+            super::check_entitlement(&state, auth, EntitledFeature::FileSearch)
+                .await
+                .is_err(),
+            "org plan must apply to JWT callers"
+        );
+        assert!(
+            super::resolve_model_degradation(&state, auth)
+                .await
+                .is_some(),
+            "org degradation ladders must apply to JWT callers"
+        );
+    }
+
     #[tokio::test]
     async fn test_request_with_invalid_api_key_format() {
         let app = test_app().await;
@@ -3235,6 +4524,99 @@ max_file_size_mb = {max_file_size_mb}
         crate::build_app(&config, state)
     }
 
+    /// Create a test application with `[features.entitlements]` enabled on
+    /// the built-in plans
+    async fn test_app_with_entitlements() -> axum::Router {
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let db_id = COUNTER.fetch_add(1, Ordering::SeqCst);
+
+        #[cfg(feature = "sso")]
+        let session_section = r#"
+[auth.session]
+secret = "test-session-secret-must-be-long-enough-for-hmac-pepper-32b"
+"#;
+        #[cfg(not(feature = "sso"))]
+        let session_section = "";
+
+        let config_str = format!(
+            r#"
+[database]
+type = "sqlite"
+path = "file:api_test_entitlements_db_{db_id}?mode=memory&cache=shared"
+create_if_missing = true
+run_migrations = true
+wal_mode = false
+busy_timeout_ms = 5000
+{session_section}
+[providers]
+default_provider = "test"
+
+[providers.test]
+type = "test"
+model_name = "test-model"
+
+[features.entitlements]
+enabled = true
+"#
+        );
+
+        let config =
+            crate::config::GatewayConfig::parse(&config_str).expect("Failed to parse test config");
+        let state = crate::AppState::new(config.clone())
+            .await
+            .expect("Failed to create AppState");
+        crate::build_app(&config, state)
+    }
+
+    #[tokio::test]
+    async fn test_vector_stores_require_file_search_entitlement() {
+        let app = test_app_with_entitlements().await;
+        let org_id = create_org_for_vector_store(&app, "vs-plan-org").await;
+        let (_, api_key_response) = post_json(
+            &app,
+            "/admin/v1/api-keys",
+            json!({"name": "plan-key", "owner": {"type": "organization", "org_id": org_id}}),
+        )
+        .await;
+        let auth = format!("Bearer {}", api_key_response["key"].as_str().unwrap());
+        let create = json!({
+            "owner": {"type": "organization", "organization_id": org_id},
+            "name": "Docs"
+        });
+
+        // The default free plan doesn't include file search
+        let (status, body) = post_json_with_headers(
+            &app,
+            "/api/v1/vector_stores",
+            create.clone(),
+            vec![("Authorization", &auth)],
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"]["code"], "feature_not_entitled");
+
+        let (status, _) = put_json(
+            &app,
+            "/admin/v1/organizations/vs-plan-org/entitlements",
+            json!({"plan": "team"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, _) = post_json_with_headers(
+            &app,
+            "/api/v1/vector_stores",
+            create,
+            vec![("Authorization", &auth)],
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
     /// Create a test application with file_search_service configured.
     ///
     /// This enables testing endpoints that require the file search service,
@@ -5966,6 +7348,8 @@ model_name = "test-model"
             file_id,
             chunk_index: 0,
             content: "This is the matching content from the document.".to_string(),
+            char_start: 0,
+            char_end: 0,
             score: 0.95,
             metadata: Some(serde_json::json!({"source": "test.pdf"})),
         }];
@@ -6032,6 +7416,8 @@ model_name = "test-model"
                 file_id,
                 chunk_index: 0,
                 content: "First result with highest score.".to_string(),
+                char_start: 0,
+                char_end: 0,
                 score: 0.98,
                 metadata: None,
             },
@@ -6041,6 +7427,8 @@ model_name = "test-model"
                 file_id,
                 chunk_index: 1,
                 content: "Second result with medium score.".to_string(),
+                char_start: 0,
+                char_end: 0,
                 score: 0.85,
                 metadata: None,
             },
@@ -6050,6 +7438,8 @@ model_name = "test-model"
                 file_id,
                 chunk_index: 2,
                 content: "Third result with lower score.".to_string(),
+                char_start: 0,
+                char_end: 0,
                 score: 0.72,
                 metadata: None,
             },
@@ -6107,6 +7497,8 @@ model_name = "test-model"
                 file_id,
                 chunk_index: i,
                 content: format!("Result {}", i),
+                char_start: 0,
+                char_end: 0,
                 score: 0.9 - (i as f64 * 0.05),
                 metadata: None,
             })
@@ -6154,6 +7546,8 @@ model_name = "test-model"
             file_id: uuid::Uuid::new_v4(),
             chunk_index: 0,
             content: "Content with metadata".to_string(),
+            char_start: 0,
+            char_end: 0,
             score: 0.9,
             metadata: Some(serde_json::json!({
                 "category": "documentation",
@@ -6210,6 +7604,8 @@ model_name = "test-model"
             file_id: uuid::Uuid::new_v4(),
             chunk_index: 0,
             content: "Content without metadata".to_string(),
+            char_start: 0,
+            char_end: 0,
             score: 0.9,
             metadata: None,
         }];
@@ -6261,6 +7657,8 @@ model_name = "test-model"
             file_id: file_uuid,
             chunk_index: 5,
             content: "Test content".to_string(),
+            char_start: 0,
+            char_end: 0,
             score: 0.88,
             metadata: None,
         }];
@@ -71,72 +71,7 @@ pub async fn api_v1_models(
             })?;
     }
 
-    // Read static provider models from the in-memory cache (warmed on startup,
-    // refreshed periodically). Providers missing from the cache (e.g. if warming
-    // failed) are fetched live as a fallback.
-    let cache_enabled = state.config.features.static_models_cache.enabled();
-    let mut hits: Vec<(String, crate::providers::ModelsResponse)> = Vec::new();
-    let mut misses: Vec<(String, &crate::config::ProviderConfig)> = Vec::new();
-    if cache_enabled {
-        let cached = state.static_models_cache.read().await;
-        for (name, cfg) in state.config.providers.iter() {
-            if let Some(resp) = cached.get(name) {
-                hits.push((name.to_owned(), resp.clone()));
-            } else {
-                misses.push((name.to_owned(), cfg));
-            }
-        }
-    } else {
-        misses.extend(
-            state
-                .config
-                .providers
-                .iter()
-                .map(|(name, cfg)| (name.to_owned(), cfg)),
-        );
-    }
-
-    // Live-fetch any providers not in the cache
-    if !misses.is_empty() {
-        use futures::future::join_all;
-
-        let futures: Vec<_> = misses
-            .into_iter()
-            .map(|(name, cfg)| {
-                let http = state.http_client.clone();
-                let cbs = state.circuit_breakers.clone();
-                async move {
-                    let result =
-                        crate::providers::list_models_for_config(cfg, &name, &http, &cbs).await;
-                    (name, result)
-                }
-            })
-            .collect();
-
-        let mut live_fetched = Vec::new();
-        for (name, result) in join_all(futures).await {
-            match result {
-                Ok(resp) => {
-                    if cache_enabled {
-                        live_fetched.push((name.clone(), resp.clone()));
-                    }
-                    hits.push((name, resp));
-                }
-                Err(e) => {
-                    tracing::warn!(provider = %name, error = %e, "Live-fetch fallback failed for cache-miss provider")
-                }
-            }
-        }
-
-        // Write successful live-fetches back to the cache so subsequent requests
-        // don't repeat the same upstream calls until the next background refresh.
-        if !live_fetched.is_empty() {
-            let mut cache = state.static_models_cache.write().await;
-            for (name, resp) in live_fetched {
-                cache.insert(name, resp);
-            }
-        }
-    }
+    let hits = static_provider_models(&state).await;
 
     // Collect successful results and enrich with catalog data
     let mut all_models = Vec::new();
@@ -270,6 +205,39 @@ pub async fn api_v1_models(
                         );
                     }
 
+                    // Dates: config overrides catalog
+                    let dates = [
+                        (
+                            "release_date",
+                            None,
+                            enrichment.as_ref().and_then(|e| e.release_date.as_ref()),
+                        ),
+                        (
+                            "knowledge_cutoff",
+                            model_config.and_then(|mc| mc.knowledge_cutoff.as_ref()),
+                            enrichment
+                                .as_ref()
+                                .and_then(|e| e.knowledge_cutoff.as_ref()),
+                        ),
+                        (
+                            "deprecation_date",
+                            model_config.and_then(|mc| mc.deprecation_date.as_ref()),
+                            enrichment
+                                .as_ref()
+                                .and_then(|e| e.deprecation_date.as_ref()),
+                        ),
+                        (
+                            "sunset_date",
+                            model_config.and_then(|mc| mc.sunset_date.as_ref()),
+                            enrichment.as_ref().and_then(|e| e.sunset_date.as_ref()),
+                        ),
+                    ];
+                    for (key, config_value, catalog_value) in dates {
+                        if let Some(date) = config_value.or(catalog_value) {
+                            obj.insert(key.to_string(), serde_json::Value::String(date.clone()));
+                        }
+                    }
+
                     // Image generation metadata (config only)
                     if let Some(mc) = model_config {
                         if !mc.image_sizes.is_empty() {
@@ -691,3 +659,80 @@ pub async fn api_v1_models(
 
     Ok(Json(CombinedModelsResponse { data: all_models }))
 }
+
+/// List the models of every static (config-file) provider.
+///
+/// Reads the in-memory cache, live-fetching providers missing from it.
+/// Providers whose models can't be fetched are left out.
+pub(crate) async fn static_provider_models(
+    state: &AppState,
+) -> Vec<(String, crate::providers::ModelsResponse)> {
+    // Read static provider models from the in-memory cache (warmed on startup,
+    // refreshed periodically). Providers missing from the cache (e.g. if warming
+    // failed) are fetched live as a fallback. Providers disabled through their
+    // runtime settings are left out.
+    let cache_enabled = state.config.features.static_models_cache.enabled();
+    let providers = state
+        .config
+        .providers
+        .iter()
+        .filter(|(name, _)| state.provider_settings.is_enabled(name));
+    let mut hits: Vec<(String, crate::providers::ModelsResponse)> = Vec::new();
+    let mut misses: Vec<(String, &crate::config::ProviderConfig)> = Vec::new();
+    if cache_enabled {
+        let cached = state.static_models_cache.read().await;
+        for (name, cfg) in providers {
+            if let Some(resp) = cached.get(name) {
+                hits.push((name.to_owned(), resp.clone()));
+            } else {
+                misses.push((name.to_owned(), cfg));
+            }
+        }
+    } else {
+        misses.extend(providers.map(|(name, cfg)| (name.to_owned(), cfg)));
+    }
+
+    // Live-fetch any providers not in the cache
+    if !misses.is_empty() {
+        use futures::future::join_all;
+
+        let futures: Vec<_> = misses
+            .into_iter()
+            .map(|(name, cfg)| {
+                let http = state.http_client.clone();
+                let cbs = state.circuit_breakers.clone();
+                async move {
+                    let result =
+                        crate::providers::list_models_for_config(cfg, &name, &http, &cbs).await;
+                    (name, result)
+                }
+            })
+            .collect();
+
+        let mut live_fetched = Vec::new();
+        for (name, result) in join_all(futures).await {
+            match result {
+                Ok(resp) => {
+                    if cache_enabled {
+                        live_fetched.push((name.clone(), resp.clone()));
+                    }
+                    hits.push((name, resp));
+                }
+                Err(e) => {
+                    tracing::warn!(provider = %name, error = %e, "Live-fetch fallback failed for cache-miss provider")
+                }
+            }
+        }
+
+        // Write successful live-fetches back to the cache so subsequent requests
+        // don't repeat the same upstream calls until the next background refresh.
+        if !live_fetched.is_empty() {
+            let mut cache = state.static_models_cache.write().await;
+            for (name, resp) in live_fetched {
+                cache.insert(name, resp);
+            }
+        }
+    }
+
+    hits
+}
@@ -333,8 +333,14 @@ pub async fn api_v1_responses_get(
     let org_id = require_caller_org(auth.as_ref(), state.default_org_id)?;
 
     if query.stream.unwrap_or(false) {
-        return stream_response_events(state.clone(), response_id, org_id, query.starting_after)
-            .await;
+        return stream_response_events(
+            state.clone(),
+            response_id,
+            org_id,
+            query.starting_after,
+            None,
+        )
+        .await;
     }
 
     let record = store
@@ -388,6 +394,10 @@ pub struct DeleteResponse {
 // Event log replay
 // ─────────────────────────────────────────────────────────────────────────────
 
+/// Fixed page size for SSE replay batches. Kept internal so the
+/// wire protocol doesn't expose a knob clients would need to tune.
+const REPLAY_PAGE_SIZE: i64 = 200;
+
 /// Stream the persisted event log as Server-Sent Events. While the
 /// response is still in-progress the handler polls the DB every
 /// 250ms for new rows; once the row reaches a terminal status and the
@@ -398,18 +408,18 @@ pub struct DeleteResponse {
 /// (`event: <type>\ndata: <payload>\n\n`) so JS SDK callers see the
 /// typed events they expect.
 ///
-/// Used by both `GET /v1/responses/{id}?stream=true` (the
-/// spec-conformant entry point) and any clients that supply the cursor
-/// query parameter.
-/// Fixed page size for SSE replay batches. Kept internal so the
-/// wire protocol doesn't expose a knob clients would need to tune.
-const REPLAY_PAGE_SIZE: i64 = 200;
-
-async fn stream_response_events(
+/// Used by `GET /v1/responses/{id}?stream=true` (the spec-conformant
+/// entry point, optionally with a cursor) and by `POST /v1/responses`
+/// for `background` + `stream` requests.
+///
+/// `preamble` is sent before any logged event; `POST /v1/responses` uses it
+/// to announce a queued background response before the worker picks it up.
+pub(crate) async fn stream_response_events(
     state: AppState,
     response_id: String,
     org_id: Uuid,
     starting_after: Option<i64>,
+    preamble: Option<bytes::Bytes>,
 ) -> Result<axum::response::Response, ApiError> {
     use bytes::Bytes;
     use http::Response as HttpResponse;
@@ -445,6 +455,11 @@ async fn stream_response_events(
             "response.cancelled",
             "response.incomplete",
         ];
+        if let Some(preamble) = preamble
+            && tx.send(Ok(preamble)).await.is_err()
+        {
+            return;
+        }
         let mut cursor = starting_after;
         loop {
             // Drain everything past the cursor.
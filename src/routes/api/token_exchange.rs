@@ -0,0 +1,220 @@
+//! `POST /v1/auth/token` — exchange an API key for a short-lived token.
+//!
+//! See [`crate::auth::exchange_token`] for how the token is signed and how
+//! the API middleware accepts it.
+
+#![cfg(feature = "server")]
+
+use axum::{Extension, Json, extract::State, http::StatusCode};
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::ApiError;
+use crate::{
+    AppState,
+    auth::{
+        ApiKeyAuth, AuthenticatedRequest, ExchangeTokenSigner,
+        exchange_token::{self, TokenRestrictions},
+    },
+    models::ApiKeyOwner,
+};
+
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct TokenExchangeRequest {
+    /// Token lifetime in seconds. Defaults to
+    /// `auth.token_exchange.default_ttl_secs`, capped at `max_ttl_secs`.
+    #[serde(default)]
+    pub expires_in: Option<u64>,
+    /// Models the token may use. Each must be allowed by the API key;
+    /// trailing wildcards work as in `allowed_models`.
+    #[serde(default)]
+    pub models: Option<Vec<String>>,
+    /// Scopes the token carries. Each must be granted by the API key.
+    #[serde(default)]
+    pub scopes: Option<Vec<String>>,
+    /// Project (ID or slug) to bind the token to. Must belong to the key's
+    /// organization, or be the key's own project.
+    #[serde(default)]
+    pub project: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct TokenExchangeResponse {
+    pub access_token: String,
+    /// Always `Bearer`
+    pub token_type: &'static str,
+    /// Lifetime in seconds
+    pub expires_in: u64,
+    pub expires_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scopes: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub models: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<Uuid>,
+}
+
+/// Exchange an API key for a short-lived token
+///
+/// Mints a JWT that browser or edge clients can send as
+/// `Authorization: Bearer <token>` instead of the API key. The token can
+/// only narrow the key (models, scopes, project), counts against the key's
+/// budget and rate limits, and stops working when the key is revoked.
+/// Requires `[auth.token_exchange]` and must be called with the API key
+/// itself, not an exchanged token.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/api/v1/auth/token",
+    tag = "auth",
+    request_body = TokenExchangeRequest,
+    responses(
+        (status = 200, description = "Token minted", body = TokenExchangeResponse),
+        (status = 400, description = "Restrictions exceed the API key", body = crate::openapi::ErrorResponse),
+        (status = 401, description = "Authentication required", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Not authenticated with an API key", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Token exchange disabled", body = crate::openapi::ErrorResponse),
+    ),
+    security(("api_key" = []))
+))]
+pub async fn api_v1_auth_token(
+    State(state): State<AppState>,
+    auth: Option<Extension<AuthenticatedRequest>>,
+    Json(req): Json<TokenExchangeRequest>,
+) -> Result<Json<TokenExchangeResponse>, ApiError> {
+    let config = &state.config.auth.token_exchange;
+    let signer = ExchangeTokenSigner::from_config(config).ok_or_else(|| {
+        ApiError::new(
+            StatusCode::NOT_FOUND,
+            "not_found",
+            "Token exchange is not enabled",
+        )
+    })?;
+
+    let api_key = auth
+        .as_ref()
+        .and_then(|Extension(auth)| auth.api_key())
+        .filter(|key| key.exchanged.is_none())
+        .ok_or_else(|| {
+            ApiError::new(
+                StatusCode::FORBIDDEN,
+                "forbidden",
+                "Token exchange requires authenticating with an API key",
+            )
+        })?;
+
+    let ttl = req.expires_in.unwrap_or(config.default_ttl_secs);
+    if ttl == 0 || ttl > config.max_ttl_secs {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "invalid_request",
+            format!("expires_in must be between 1 and {}", config.max_ttl_secs),
+        ));
+    }
+
+    exchange_token::narrow(&api_key.key, req.models.as_deref(), req.scopes.as_deref())
+        .map_err(|msg| ApiError::new(StatusCode::BAD_REQUEST, "invalid_request", msg))?;
+
+    let project_id = match &req.project {
+        Some(project) => Some(resolve_project(&state, api_key, project).await?),
+        None => None,
+    };
+
+    let (access_token, claims) = signer
+        .mint(
+            api_key,
+            TokenRestrictions {
+                models: req.models,
+                scopes: req.scopes,
+                project_id,
+            },
+            ttl,
+        )
+        .map_err(|e| {
+            tracing::error!(error = ?e, "Failed to mint exchanged token");
+            ApiError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "internal_error",
+                "Failed to mint token",
+            )
+        })?;
+
+    tracing::debug!(
+        api_key_id = %api_key.key.id,
+        jti = %claims.jti,
+        ttl,
+        "Exchanged API key for short-lived token"
+    );
+
+    Ok(Json(TokenExchangeResponse {
+        access_token,
+        token_type: "Bearer",
+        expires_in: ttl,
+        expires_at: Utc
+            .timestamp_opt(claims.exp, 0)
+            .single()
+            .unwrap_or_default(),
+        scopes: claims.scopes,
+        models: claims.models,
+        project_id: claims.project_id,
+    }))
+}
+
+/// Resolve `project` (ID or slug) and check the key may act on it.
+///
+/// Project-scoped keys can only name their own project. Organization and
+/// service account keys can name any project in their org; team keys only
+/// projects of their team. User keys have no org to narrow within.
+async fn resolve_project(
+    state: &AppState,
+    api_key: &ApiKeyAuth,
+    project: &str,
+) -> Result<Uuid, ApiError> {
+    let not_allowed = || {
+        ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "invalid_request",
+            format!("Project '{project}' is not accessible to this API key"),
+        )
+    };
+    let services = state.services.as_ref().ok_or_else(|| {
+        ApiError::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "not_configured",
+            "Database not configured",
+        )
+    })?;
+    let org_id = match (&api_key.key.owner, api_key.org_id) {
+        (ApiKeyOwner::User { .. }, _) | (_, None) => return Err(not_allowed()),
+        (_, Some(org_id)) => org_id,
+    };
+
+    let found = match Uuid::parse_str(project) {
+        Ok(id) => services.projects.get_by_id_and_org(id, org_id).await,
+        Err(_) => services.projects.get_by_slug(org_id, project).await,
+    }
+    .map_err(|e| {
+        tracing::error!(error = %e, "Failed to look up project for token exchange");
+        ApiError::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "internal_error",
+            "Failed to look up project",
+        )
+    })?
+    .ok_or_else(not_allowed)?;
+
+    let allowed = match api_key.project_id {
+        Some(own) => found.id == own,
+        None => api_key
+            .team_id
+            .is_none_or(|team| found.team_id == Some(team)),
+    };
+    if allowed {
+        Ok(found.id)
+    } else {
+        Err(not_allowed())
+    }
+}
@@ -0,0 +1,147 @@
+use axum::{Extension, Json, extract::State};
+use axum_valid::Valid;
+use http::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use validator::Validate;
+
+use super::{ApiError, check_model_access};
+use crate::{
+    AppState,
+    auth::AuthenticatedRequest,
+    routing::{resolver, route_model_extended},
+};
+
+#[derive(Debug, Deserialize, Serialize, Validate)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct TokenizeRequest {
+    /// Model to count for, routed like a request's `model`
+    #[validate(length(min = 1, max = 512))]
+    pub model: String,
+    /// Chat messages, as sent to `/v1/chat/completions`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "utoipa", schema(value_type = Option<Vec<Object>>))]
+    pub messages: Option<Vec<Value>>,
+    /// Input as sent to `/v1/responses` or `/v1/embeddings`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "utoipa", schema(value_type = Option<Object>))]
+    pub input: Option<Value>,
+    /// Prompt as sent to `/v1/completions`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "utoipa", schema(value_type = Option<Object>))]
+    pub prompt: Option<Value>,
+    /// Responses API instructions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+    /// Tool definitions, which count toward the prompt
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "utoipa", schema(value_type = Option<Vec<Object>>))]
+    pub tools: Option<Vec<Value>>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct TokenizeResponse {
+    /// Provider the model routes to
+    pub provider: String,
+    /// Model as sent to the provider
+    pub model: String,
+    /// Prompt tokens, including chat framing and tool definitions
+    pub input_tokens: u64,
+    /// `o200k_base`, `cl100k_base`, `huggingface`, `anthropic` or `estimate`
+    pub tokenizer: String,
+    /// Whether the count is an estimate of about 4 characters per token
+    pub estimated: bool,
+}
+
+/// Count tokens
+///
+/// Counts the prompt tokens of a request with the model's own tokenizer:
+/// tiktoken for OpenAI models, Anthropic's counting API for Anthropic
+/// providers, and configured Hugging Face tokenizers for open models. Other
+/// models are estimated. Nothing is sent for generation and no usage is
+/// recorded.
+///
+/// **Hadrian Extension:** This endpoint is not part of the OpenAI API specification.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/api/v1/tokenize",
+    tag = "tokenize",
+    request_body(
+        content = TokenizeRequest,
+        example = json!({
+            "model": "openai/gpt-4o",
+            "messages": [
+                {"role": "system", "content": "You are a helpful assistant."},
+                {"role": "user", "content": "Hello!"}
+            ]
+        })
+    ),
+    responses(
+        (status = 200, description = "Token count", body = TokenizeResponse,
+            example = json!({
+                "provider": "openai",
+                "model": "gpt-4o",
+                "input_tokens": 19,
+                "tokenizer": "o200k_base",
+                "estimated": false
+            })
+        ),
+        (status = 400, description = "Bad request - invalid model", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Token counting not configured", body = crate::openapi::ErrorResponse),
+    ),
+    security(("api_key" = []))
+))]
+#[tracing::instrument(name = "api.tokenize", skip(state, auth, payload), fields(model = %payload.model))]
+pub async fn api_v1_tokenize(
+    State(state): State<AppState>,
+    auth: Option<Extension<AuthenticatedRequest>>,
+    Valid(Json(payload)): Valid<Json<TokenizeRequest>>,
+) -> Result<Json<TokenizeResponse>, ApiError> {
+    let counter = state.token_counter.clone().ok_or_else(|| {
+        ApiError::new(
+            StatusCode::NOT_FOUND,
+            "feature_not_configured",
+            "Token counting is not configured",
+        )
+    })?;
+    check_model_access(&state, auth.as_ref(), [payload.model.as_str()]).await?;
+
+    let routed = route_model_extended(Some(&payload.model), &state.config.providers)?;
+    let resolved = resolver::resolve_to_provider(
+        routed,
+        state.db.as_ref(),
+        state.cache.as_ref(),
+        state.secrets.as_ref(),
+        &state.provider_settings,
+        auth.as_ref().map(|e| &e.0),
+    )
+    .await
+    .map_err(|e| {
+        ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "provider_resolution_error",
+            format!("Failed to resolve provider: {}", e),
+        )
+    })?;
+
+    let body = serde_json::to_value(&payload).unwrap_or(Value::Null);
+    let count = counter
+        .count(
+            &state,
+            &resolved.provider_name,
+            &resolved.provider_config,
+            &payload.model,
+            &resolved.model,
+            &body,
+        )
+        .await;
+
+    Ok(Json(TokenizeResponse {
+        provider: resolved.provider_name,
+        model: resolved.model,
+        input_tokens: count.tokens,
+        estimated: !count.is_exact(),
+        tokenizer: count.tokenizer.to_string(),
+    }))
+}
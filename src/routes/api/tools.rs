@@ -349,6 +349,20 @@ pub async fn web_search(
             tool_results_count: Some(results_count),
             tool_runtime_seconds: None,
             tool_exit_code: None,
+            jwt_subject: None,
+            error_code: None,
+            structured_output_repairs: None,
+            context_original_tokens: None,
+            context_compressed_tokens: None,
+            degraded_from_model: None,
+            degradation_reason: None,
+            ttft_ms: None,
+            inter_token_latency_ms: None,
+            output_tokens_per_second: None,
+            idempotency_key: None,
+            provider_attempts: None,
+            suspected_duplicate_attempts: None,
+            tags: None,
         });
     }
 
@@ -377,94 +391,91 @@ pub struct WebFetchResponse {
     pub content_length: usize,
 }
 
-/// Fetch a web page
-///
-/// Fetches a URL and returns its content, optionally stripping HTML tags.
-///
-/// **Hadrian Extension:** This endpoint is not part of the OpenAI API specification.
-#[cfg_attr(feature = "utoipa", utoipa::path(
-    post,
-    path = "/api/v1/tools/web-fetch",
-    tag = "Tools",
-    request_body = WebFetchRequest,
-    responses(
-        (status = 200, description = "Fetched content", body = WebFetchResponse),
-        (status = 400, description = "Bad request or blocked URL"),
-        (status = 404, description = "Web fetch not configured"),
-    ),
-    security(("api_key" = []))
-))]
-#[tracing::instrument(name = "api.tools.web_fetch", skip(state, auth, authz))]
-pub async fn web_fetch(
-    State(state): State<AppState>,
-    auth: Option<Extension<AuthenticatedRequest>>,
-    authz: Option<Extension<AuthzContext>>,
-    Valid(Json(payload)): Valid<Json<WebFetchRequest>>,
-) -> Result<Json<WebFetchResponse>, ApiError> {
-    // Authz check
-    if let Some(Extension(ref authz)) = authz {
-        let org_id = auth.as_ref().and_then(|a| {
-            a.api_key()
-                .and_then(|k| k.org_id.map(|id| id.to_string()))
-                .or_else(|| a.identity().and_then(|i| i.org_ids.first().cloned()))
-        });
-        let project_id = auth.as_ref().and_then(|a| {
-            a.api_key()
-                .and_then(|k| k.project_id.map(|id| id.to_string()))
-                .or_else(|| a.identity().and_then(|i| i.project_ids.first().cloned()))
-        });
-        authz
-            .require_api(
-                "tool",
-                "execute",
-                Some("web_fetch"),
-                None,
-                org_id.as_deref(),
-                project_id.as_deref(),
-            )
-            .await
-            .map_err(|e| {
-                ApiError::new(StatusCode::FORBIDDEN, "authorization_denied", e.to_string())
-            })?;
+/// A fetched page, converted to text when it was HTML.
+pub struct FetchedPage {
+    pub content_type: Option<String>,
+    pub content: String,
+    /// Body bytes read before HTML conversion.
+    pub bytes_fetched: usize,
+}
+
+/// Errors from the core web fetch execution.
+#[derive(Debug)]
+pub enum WebFetchError {
+    InvalidUrl,
+    DomainNotAllowed(String),
+    Client,
+    RequestFailed,
+    Redirect,
+    UpstreamStatus(u16),
+    ContentType(Option<String>),
+    ReadFailed,
+}
+
+impl WebFetchError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Self::InvalidUrl | Self::Redirect => StatusCode::BAD_REQUEST,
+            Self::DomainNotAllowed(_) => StatusCode::FORBIDDEN,
+            Self::Client => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::RequestFailed | Self::UpstreamStatus(_) | Self::ReadFailed => {
+                StatusCode::BAD_GATEWAY
+            }
+            Self::ContentType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        }
     }
 
-    let config = state.config.features.web_fetch.as_ref().ok_or_else(|| {
-        ApiError::new(
-            http::StatusCode::NOT_FOUND,
-            "feature_not_configured",
-            "Web fetch is not configured",
-        )
-    })?;
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidUrl => "invalid_url",
+            Self::DomainNotAllowed(_) => "domain_not_allowed",
+            Self::Client => "client_error",
+            Self::RequestFailed | Self::ReadFailed => "fetch_failed",
+            Self::Redirect => "redirect_blocked",
+            Self::UpstreamStatus(_) => "upstream_error",
+            Self::ContentType(_) => "unsupported_content_type",
+        }
+    }
+}
 
-    if !config.enabled {
-        return Err(ApiError::new(
-            http::StatusCode::NOT_FOUND,
-            "feature_disabled",
-            "Web fetch is disabled",
-        ));
+impl std::fmt::Display for WebFetchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidUrl => write!(f, "Invalid or blocked URL"),
+            Self::DomainNotAllowed(host) => write!(f, "Host '{host}' is not in the allowlist"),
+            Self::Client => write!(f, "Failed to build HTTP client"),
+            Self::RequestFailed => write!(f, "Failed to fetch URL"),
+            Self::Redirect => write!(f, "URL returned a redirect, which is not allowed"),
+            Self::UpstreamStatus(code) => write!(f, "URL returned status {code}"),
+            Self::ContentType(Some(ct)) => write!(f, "Content type '{ct}' is not allowed"),
+            Self::ContentType(None) => write!(f, "Response has no Content-Type header"),
+            Self::ReadFailed => write!(f, "Failed to read response body"),
+        }
     }
+}
 
+/// Fetch a URL with SSRF protection, the domain allowlist, and the
+/// configured content-type and size limits.
+///
+/// This is the core fetch logic, shared by the REST endpoint and the
+/// server-side web_fetch tool.
+pub async fn execute_web_fetch(
+    config: &crate::config::WebFetchConfig,
+    url_options: UrlValidationOptions,
+    url: &str,
+    max_bytes: usize,
+) -> Result<FetchedPage, WebFetchError> {
     // SSRF protection — validate URL and capture resolved IPs to pin the request
-    let validated = validate_base_url_opts(
-        &payload.url,
-        UrlValidationOptions {
-            allow_loopback: state.config.server.allow_loopback_urls,
-            allow_private: state.config.server.allow_private_urls,
-        },
-    )
-    .map_err(|e| {
-        tracing::warn!(url = %payload.url, error = %e, "URL validation failed");
-        ApiError::new(
-            http::StatusCode::BAD_REQUEST,
-            "invalid_url",
-            "Invalid or blocked URL",
-        )
+    let validated = validate_base_url_opts(url, url_options).map_err(|e| {
+        tracing::warn!(url = %url, error = %e, "URL validation failed");
+        WebFetchError::InvalidUrl
     })?;
 
-    let max_bytes = payload
-        .max_length
-        .unwrap_or(config.max_response_bytes)
-        .min(config.max_response_bytes);
+    if !config.is_domain_allowed(&validated.host) {
+        tracing::warn!(url = %url, host = %validated.host, "Web fetch host not in allowlist");
+        return Err(WebFetchError::DomainNotAllowed(validated.host.clone()));
+    }
+
     let timeout = std::time::Duration::from_secs(config.timeout_secs);
 
     // Build a one-shot client pinned to the validated IPs to prevent DNS rebinding.
@@ -480,48 +491,26 @@ pub async fn web_fetch(
     }
     let client = builder.build().map_err(|e| {
         tracing::error!(error = %e, "Failed to build pinned HTTP client");
-        ApiError::new(
-            http::StatusCode::INTERNAL_SERVER_ERROR,
-            "client_error",
-            "Failed to build HTTP client",
-        )
+        WebFetchError::Client
     })?;
 
-    let resp = client
-        .get(&payload.url)
-        .timeout(timeout)
-        .send()
-        .await
-        .map_err(|e| {
-            tracing::error!(error = %e, url = %payload.url, "Web fetch request failed");
-            ApiError::new(
-                http::StatusCode::BAD_GATEWAY,
-                "fetch_failed",
-                "Failed to fetch URL",
-            )
-        })?;
+    let resp = client.get(url).timeout(timeout).send().await.map_err(|e| {
+        tracing::error!(error = %e, url = %url, "Web fetch request failed");
+        WebFetchError::RequestFailed
+    })?;
 
     // Reject redirects — the validated URL must be the final destination
     if resp.status().is_redirection() {
         tracing::warn!(
-            url = %payload.url,
+            url = %url,
             status = %resp.status(),
             "URL returned a redirect, which is blocked for SSRF protection"
         );
-        return Err(ApiError::new(
-            http::StatusCode::BAD_REQUEST,
-            "redirect_blocked",
-            "URL returned a redirect, which is not allowed",
-        ));
+        return Err(WebFetchError::Redirect);
     }
 
     if !resp.status().is_success() {
-        let status_code = resp.status().as_u16();
-        return Err(ApiError::new(
-            http::StatusCode::BAD_GATEWAY,
-            "upstream_error",
-            format!("URL returned status {status_code}"),
-        ));
+        return Err(WebFetchError::UpstreamStatus(resp.status().as_u16()));
     }
 
     let content_type = resp
@@ -532,28 +521,15 @@ pub async fn web_fetch(
 
     // Check content type
     if !config.allowed_content_types.is_empty() {
-        match content_type {
-            Some(ref ct) => {
-                let ct_lower = ct.to_lowercase();
-                let allowed = config
-                    .allowed_content_types
-                    .iter()
-                    .any(|allowed| ct_lower.starts_with(allowed));
-                if !allowed {
-                    return Err(ApiError::new(
-                        http::StatusCode::UNSUPPORTED_MEDIA_TYPE,
-                        "unsupported_content_type",
-                        format!("Content type '{ct}' is not allowed"),
-                    ));
-                }
-            }
-            None => {
-                return Err(ApiError::new(
-                    http::StatusCode::UNSUPPORTED_MEDIA_TYPE,
-                    "unsupported_content_type",
-                    "Response has no Content-Type header",
-                ));
-            }
+        let allowed = content_type.as_deref().is_some_and(|ct| {
+            let ct_lower = ct.to_lowercase();
+            config
+                .allowed_content_types
+                .iter()
+                .any(|allowed| ct_lower.starts_with(allowed))
+        });
+        if !allowed {
+            return Err(WebFetchError::ContentType(content_type));
         }
     }
 
@@ -563,11 +539,7 @@ pub async fn web_fetch(
     while let Some(chunk) = stream.next().await {
         let chunk = chunk.map_err(|e| {
             tracing::error!(error = %e, "Failed to read response body");
-            ApiError::new(
-                http::StatusCode::BAD_GATEWAY,
-                "fetch_failed",
-                "Failed to read response body",
-            )
+            WebFetchError::ReadFailed
         })?;
         buf.extend_from_slice(&chunk);
         if buf.len() >= max_bytes {
@@ -587,7 +559,7 @@ pub async fn web_fetch(
     } else {
         &lossy
     };
-    let bytes_fetched = text.len() as i64;
+    let bytes_fetched = text.len();
 
     // Convert HTML to readable text
     let is_html = content_type
@@ -600,6 +572,99 @@ pub async fn web_fetch(
         text.to_string()
     };
 
+    Ok(FetchedPage {
+        content_type,
+        content,
+        bytes_fetched,
+    })
+}
+
+/// Fetch a web page
+///
+/// Fetches a URL and returns its content, optionally stripping HTML tags.
+///
+/// **Hadrian Extension:** This endpoint is not part of the OpenAI API specification.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/api/v1/tools/web-fetch",
+    tag = "Tools",
+    request_body = WebFetchRequest,
+    responses(
+        (status = 200, description = "Fetched content", body = WebFetchResponse),
+        (status = 400, description = "Bad request or blocked URL"),
+        (status = 403, description = "Host not in the configured allowlist"),
+        (status = 404, description = "Web fetch not configured"),
+    ),
+    security(("api_key" = []))
+))]
+#[tracing::instrument(name = "api.tools.web_fetch", skip(state, auth, authz))]
+pub async fn web_fetch(
+    State(state): State<AppState>,
+    auth: Option<Extension<AuthenticatedRequest>>,
+    authz: Option<Extension<AuthzContext>>,
+    Valid(Json(payload)): Valid<Json<WebFetchRequest>>,
+) -> Result<Json<WebFetchResponse>, ApiError> {
+    // Authz check
+    if let Some(Extension(ref authz)) = authz {
+        let org_id = auth.as_ref().and_then(|a| {
+            a.api_key()
+                .and_then(|k| k.org_id.map(|id| id.to_string()))
+                .or_else(|| a.identity().and_then(|i| i.org_ids.first().cloned()))
+        });
+        let project_id = auth.as_ref().and_then(|a| {
+            a.api_key()
+                .and_then(|k| k.project_id.map(|id| id.to_string()))
+                .or_else(|| a.identity().and_then(|i| i.project_ids.first().cloned()))
+        });
+        authz
+            .require_api(
+                "tool",
+                "execute",
+                Some("web_fetch"),
+                None,
+                org_id.as_deref(),
+                project_id.as_deref(),
+            )
+            .await
+            .map_err(|e| {
+                ApiError::new(StatusCode::FORBIDDEN, "authorization_denied", e.to_string())
+            })?;
+    }
+
+    let config = state.config.features.web_fetch.as_ref().ok_or_else(|| {
+        ApiError::new(
+            http::StatusCode::NOT_FOUND,
+            "feature_not_configured",
+            "Web fetch is not configured",
+        )
+    })?;
+
+    if !config.enabled {
+        return Err(ApiError::new(
+            http::StatusCode::NOT_FOUND,
+            "feature_disabled",
+            "Web fetch is disabled",
+        ));
+    }
+
+    let max_bytes = payload
+        .max_length
+        .unwrap_or(config.max_response_bytes)
+        .min(config.max_response_bytes);
+    let fetched = execute_web_fetch(
+        config,
+        UrlValidationOptions {
+            allow_loopback: state.config.server.allow_loopback_urls,
+            allow_private: state.config.server.allow_private_urls,
+        },
+        &payload.url,
+        max_bytes,
+    )
+    .await
+    .map_err(|e| ApiError::new(e.status(), e.code(), e.to_string()))?;
+    let bytes_fetched = fetched.bytes_fetched as i64;
+    let content_type = fetched.content_type;
+    let content = fetched.content;
     let content_length = content.len();
 
     // Log tool usage with identity
@@ -642,6 +707,20 @@ pub async fn web_fetch(
             tool_results_count: None,
             tool_runtime_seconds: None,
             tool_exit_code: None,
+            jwt_subject: None,
+            error_code: None,
+            structured_output_repairs: None,
+            context_original_tokens: None,
+            context_compressed_tokens: None,
+            degraded_from_model: None,
+            degradation_reason: None,
+            ttft_ms: None,
+            inter_token_latency_ms: None,
+            output_tokens_per_second: None,
+            idempotency_key: None,
+            provider_attempts: None,
+            suspected_duplicate_attempts: None,
+            tags: None,
         });
     }
 
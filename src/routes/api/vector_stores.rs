@@ -9,8 +9,8 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use super::{
-    ApiError, SortOrder, check_resource_access_optional, extract_identity_memberships,
-    get_services, validate_embedding_model_compatibility,
+    ApiError, SortOrder, check_entitlement, check_resource_access_optional,
+    extract_identity_memberships, get_services, validate_embedding_model_compatibility,
 };
 use crate::{
     AppState,
@@ -18,10 +18,11 @@ use crate::{
     db::ListParams,
     middleware::AuthzContext,
     models::{
-        AddFileToVectorStore, AttributeFilter, ChunkingStrategy, CreateVectorStore, FileId,
-        FileSearchRankingOptions, UpdateVectorStore, VectorStore, VectorStoreFile,
-        VectorStoreFileId, VectorStoreFileStatus, VectorStoreId, VectorStoreOwner,
-        VectorStoreOwnerType, chunk_id_serde, file_id_serde, vector_store_id_serde,
+        AddFileToVectorStore, AttributeFilter, ChunkingStrategy, CreateVectorStore,
+        EntitledFeature, FileId, FileSearchRankingOptions, UpdateVectorStore, VectorStore,
+        VectorStoreFile, VectorStoreFileId, VectorStoreFileStatus, VectorStoreId, VectorStoreOwner,
+        VectorStoreOwnerType, VectorStoreStatus, chunk_id_serde, file_id_serde,
+        vector_store_id_serde,
     },
     openapi::PaginationMeta,
 };
@@ -194,6 +195,8 @@ pub async fn api_v1_vector_stores_create(
     authz: Option<Extension<AuthzContext>>,
     Valid(Json(input)): Valid<Json<CreateVectorStore>>,
 ) -> Result<(StatusCode, Json<VectorStore>), ApiError> {
+    check_entitlement(&state, auth.as_ref(), EntitledFeature::FileSearch).await?;
+
     // Check RAG feature access via CEL policies
     if let Some(Extension(ref authz)) = authz {
         let org_id = auth
@@ -757,6 +760,21 @@ pub async fn api_v1_vector_stores_delete(
     }))
 }
 
+/// Reject writes to a store that the cleanup worker has expired.
+fn ensure_not_expired(vector_store: &VectorStore) -> Result<(), ApiError> {
+    if vector_store.status == VectorStoreStatus::Expired {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "vector_store_expired",
+            format!(
+                "Vector store '{}' has expired; create a new one or remove its expires_after policy",
+                vector_store.id
+            ),
+        ));
+    }
+    Ok(())
+}
+
 // ============================================================================
 // Vector Store File Route Handlers
 // ============================================================================
@@ -807,6 +825,8 @@ pub async fn api_v1_vector_stores_create_file(
     Path(vector_store_id): Path<VectorStoreId>,
     Json(input): Json<CreateVectorStoreFileRequest>,
 ) -> Result<(StatusCode, Json<VectorStoreFile>), ApiError> {
+    check_entitlement(&state, auth.as_ref(), EntitledFeature::FileSearch).await?;
+
     let vector_store_id = vector_store_id.into_inner();
     let services = get_services(&state)?;
 
@@ -828,6 +848,7 @@ pub async fn api_v1_vector_stores_create_file(
         vector_store.owner_type,
         vector_store.owner_id,
     )?;
+    ensure_not_expired(&vector_store)?;
 
     // Check files-per-vector-store limit
     let max = state
@@ -1460,6 +1481,8 @@ pub async fn api_v1_vector_stores_create_file_batch(
     Path(vector_store_id): Path<VectorStoreId>,
     Json(input): Json<CreateFileBatchRequest>,
 ) -> Result<(StatusCode, Json<FileBatch>), ApiError> {
+    check_entitlement(&state, auth.as_ref(), EntitledFeature::FileSearch).await?;
+
     let vector_store_id = vector_store_id.into_inner();
     let services = get_services(&state)?;
 
@@ -1481,6 +1504,7 @@ pub async fn api_v1_vector_stores_create_file_batch(
         vector_store.owner_type,
         vector_store.owner_id,
     )?;
+    ensure_not_expired(&vector_store)?;
 
     // Cap batch size to prevent oversized requests from causing expensive DB operations
     const MAX_BATCH_SIZE: usize = 500;
@@ -2062,6 +2086,78 @@ pub async fn api_v1_vector_stores_list_file_chunks(
     }))
 }
 
+/// A single content part of a vector store file.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct VectorStoreFileContentPart {
+    /// Content type (always "text")
+    #[serde(rename = "type")]
+    pub content_type: String,
+    /// Text of one parsed chunk
+    pub text: String,
+}
+
+/// Parsed content of a vector store file (OpenAI-compatible).
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct VectorStoreFileContentResponse {
+    /// Object type (always "vector_store.file_content.page")
+    pub object: String,
+    /// Parsed chunks in file order
+    pub data: Vec<VectorStoreFileContentPart>,
+    /// Whether more content is available (always false; the whole file is returned)
+    pub has_more: bool,
+    /// Cursor for the next page (always null)
+    pub next_page: Option<String>,
+}
+
+/// Retrieve parsed file content
+///
+/// Returns the parsed text of a file in a vector store, one content part per chunk
+/// in chunk order. Files that are still processing return an empty `data` array.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/api/v1/vector_stores/{vector_store_id}/files/{file_id}/content",
+    tag = "vector-stores",
+    operation_id = "vector_store_file_content_get",
+    params(
+        ("vector_store_id" = Uuid, Path, description = "Vector store ID"),
+        ("file_id" = Uuid, Path, description = "File ID"),
+    ),
+    responses(
+        (status = 200, description = "Parsed file content", body = VectorStoreFileContentResponse),
+        (status = 404, description = "Vector store or file not found", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "File search not configured", body = crate::openapi::ErrorResponse),
+    ),
+    security(("api_key" = []))
+))]
+#[tracing::instrument(skip(state, auth))]
+pub async fn api_v1_vector_stores_get_file_content(
+    State(state): State<AppState>,
+    auth: Option<Extension<AuthenticatedRequest>>,
+    Path((vector_store_id, file_id)): Path<(VectorStoreId, FileId)>,
+) -> Result<Json<VectorStoreFileContentResponse>, ApiError> {
+    let mut chunks =
+        api_v1_vector_stores_list_file_chunks(State(state), auth, Path((vector_store_id, file_id)))
+            .await?
+            .0
+            .data;
+    chunks.sort_by_key(|c| c.chunk_index);
+
+    Ok(Json(VectorStoreFileContentResponse {
+        object: "vector_store.file_content.page".to_string(),
+        data: chunks
+            .into_iter()
+            .map(|c| VectorStoreFileContentPart {
+                content_type: "text".to_string(),
+                text: c.content,
+            })
+            .collect(),
+        has_more: false,
+        next_page: None,
+    }))
+}
+
 /// Search a vector store
 ///
 /// Performs a semantic search against a vector store (OpenAI-compatible endpoint).
@@ -2091,6 +2187,8 @@ pub async fn api_v1_vector_stores_search(
     Path(vector_store_id): Path<VectorStoreId>,
     Json(input): Json<VectorStoreSearchRequest>,
 ) -> Result<Json<VectorStoreSearchResponse>, ApiError> {
+    check_entitlement(&state, auth.as_ref(), EntitledFeature::FileSearch).await?;
+
     // Check RAG feature access via CEL policies
     if let Some(Extension(ref authz)) = authz {
         let org_id = auth
@@ -2182,6 +2280,11 @@ pub async fn api_v1_vector_stores_search(
                 "not_found",
                 format!("VectorStore '{}' not found", id),
             ),
+            crate::services::FileSearchError::VectorStoreExpired(id) => ApiError::new(
+                StatusCode::BAD_REQUEST,
+                "vector_store_expired",
+                format!("VectorStore '{}' has expired", id),
+            ),
             crate::services::FileSearchError::EmbeddingError(msg) => ApiError::new(
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "embedding_error",
@@ -8,6 +8,7 @@
 //! - `/auth/logout` - Logs out and optionally redirects to IdP logout
 //! - `/auth/me` - Returns the current user's identity
 //! - `/auth/discover` - Discovers SSO configuration for an email domain
+//! - `/auth/invitations/accept` - Opens an invite link, then redirects to sign in
 //!
 //! ## SAML Routes
 //! - `/auth/saml/login` - Generates AuthnRequest and redirects to SAML IdP
@@ -34,8 +35,11 @@ use validator::ValidateEmail;
 
 use crate::{
     AppState,
-    auth::AuthError,
-    config::{SameSite, TrustedProxiesConfig},
+    auth::{
+        AuthError,
+        session_store::{DeviceInfo, OidcSession, SessionStore, enforce_session_limit},
+    },
+    config::{EnhancedSessionConfig, SameSite, TrustedProxiesConfig},
     middleware::AdminAuth,
     models::{DomainVerificationStatus, SsoEnforcementMode, SsoProviderType},
     services::audit_logs::{AuthEventParams, auth_events},
@@ -161,6 +165,17 @@ pub struct DiscoverQuery {
     pub email: String,
 }
 
+/// Query parameters for the invitation accept endpoint.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(IntoParams))]
+pub struct AcceptInvitationQuery {
+    /// Token from the invite link
+    pub token: String,
+}
+
+/// Cookie holding an opened invite link's token until the user signs in.
+const INVITATION_COOKIE: &str = "hadrian_invitation";
+
 /// Response for the /auth/discover endpoint.
 #[derive(Debug, Serialize)]
 #[cfg_attr(feature = "utoipa", derive(ToSchema))]
@@ -439,7 +454,7 @@ pub async fn callback(
 ) -> Result<Response, AuthError> {
     use std::sync::Arc;
 
-    use crate::auth::{OidcAuthenticator, session_store::DeviceInfo};
+    use crate::auth::OidcAuthenticator;
 
     // Check for error from IdP
     if let Some(error) = &query.error {
@@ -539,43 +554,11 @@ pub async fn callback(
     };
 
     // Build device info if enhanced sessions are enabled
-    let device_info = if session_config.enhanced.enabled && session_config.enhanced.track_devices {
-        let user_agent = headers
-            .get(axum::http::header::USER_AGENT)
-            .and_then(|v| v.to_str().ok())
-            .map(|s| s.to_string());
-
-        // Generate device ID from user agent (SHA256 hash, first 16 chars)
-        let device_id = user_agent.as_ref().map(|ua| {
-            use sha2::{Digest, Sha256};
-            let mut hasher = Sha256::new();
-            hasher.update(ua.as_bytes());
-            let hash = hasher.finalize();
-            hex::encode(&hash[..8])
-        });
-
-        // Parse user agent for human-readable description
-        let device_description = user_agent.as_ref().map(|ua| parse_user_agent(ua));
-
-        // Extract client IP address from headers using trusted proxy configuration
-        // Note: ConnectInfo (direct TCP connection IP) is not available in the callback;
-        // most production deployments use reverse proxies, so X-Forwarded-For is the primary source
-        let ip_address = extract_client_ip_from_parts(
-            &headers,
-            None, // No ConnectInfo available in this context
-            &state.config.server.trusted_proxies,
-        )
-        .map(|ip| ip.to_string());
-
-        Some(DeviceInfo::new(
-            user_agent,
-            ip_address,
-            device_id,
-            device_description,
-        ))
-    } else {
-        None
-    };
+    let device_info = build_device_info(
+        &headers,
+        &state.config.server.trusted_proxies,
+        &session_config.enhanced,
+    );
 
     // Extract IP and user agent for audit log (before device_info is moved)
     let audit_ip_address = device_info.as_ref().and_then(|d| d.ip_address.clone());
@@ -586,6 +569,15 @@ pub async fn callback(
         .exchange_code_with_device(&query.code, &query.state, device_info)
         .await?;
 
+    enforce_session_limit_with_audit(
+        &state,
+        registry.session_store().as_ref(),
+        &session,
+        &session_config.enhanced,
+        "oidc",
+    )
+    .await;
+
     // Set session cookie
     let same_site = match session_config.same_site {
         SameSite::Strict => CookieSameSite::Strict,
@@ -612,6 +604,15 @@ pub async fn callback(
         "OIDC session created"
     );
 
+    accept_invitations_on_sign_in(
+        &state,
+        &cookies,
+        &session,
+        audit_ip_address.clone(),
+        audit_user_agent.clone(),
+    )
+    .await;
+
     // Log successful authentication to audit log
     if let Some(services) = &state.services {
         let _ = services
@@ -725,6 +726,83 @@ pub async fn me(Extension(admin_auth): Extension<AdminAuth>) -> Json<MeResponse>
     })
 }
 
+/// Open an invite link.
+///
+/// Remembers the invitation in a short-lived cookie and redirects to sign
+/// in through the organization's SSO connection (or to the home page if it
+/// has none). The invitation is accepted when the callback creates a session
+/// for the invited email address.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/auth/invitations/accept",
+    tag = "auth",
+    operation_id = "auth_accept_invitation",
+    params(AcceptInvitationQuery),
+    responses(
+        (status = 303, description = "Redirect to sign in"),
+        (status = 403, description = "The invite link is invalid, expired, revoked or already used", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "auth.accept_invitation", skip(state, cookies, query))]
+pub async fn accept_invitation(
+    State(state): State<AppState>,
+    cookies: Cookies,
+    Query(query): Query<AcceptInvitationQuery>,
+) -> Result<Response, AuthError> {
+    let invalid = || AuthError::Forbidden("This invite link is no longer valid".to_string());
+    if !state.config.features.invitations.enabled {
+        return Err(invalid());
+    }
+    let services = state
+        .services
+        .as_ref()
+        .ok_or_else(|| AuthError::Internal("Database not configured".to_string()))?;
+
+    let invitation = services
+        .invitations
+        .get_by_token(&query.token)
+        .await
+        .map_err(|e| AuthError::Internal(e.to_string()))?
+        .filter(|i| i.status == crate::models::InvitationStatus::Pending)
+        .ok_or_else(invalid)?;
+    let org = services
+        .organizations
+        .get_by_id(invitation.org_id)
+        .await
+        .map_err(|e| AuthError::Internal(e.to_string()))?
+        .ok_or_else(invalid)?;
+
+    // The cookie must survive the round trip through the IdP, including the
+    // cross-site POST of a SAML response, which needs SameSite=None.
+    let session_config = state.config.auth.session_config_or_default();
+    let same_site = if session_config.secure {
+        CookieSameSite::None
+    } else {
+        CookieSameSite::Lax
+    };
+    let max_age = (invitation.expires_at - Utc::now()).num_seconds().max(0);
+    cookies.add(
+        Cookie::build((INVITATION_COOKIE, query.token))
+            .path("/auth")
+            .http_only(true)
+            .secure(session_config.secure)
+            .same_site(same_site)
+            .max_age(CookieDuration::seconds(max_age))
+            .build(),
+    );
+
+    let has_sso = matches!(
+        services.org_sso_configs.get_by_org_id(org.id).await,
+        Ok(Some(_))
+    );
+    let redirect_to = if has_sso {
+        format!("/auth/login?org={}", org.slug)
+    } else {
+        "/".to_string()
+    };
+    Ok(Redirect::to(&redirect_to).into_response())
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // SAML Route Handlers
 // ─────────────────────────────────────────────────────────────────────────────
@@ -845,9 +923,15 @@ pub async fn saml_acs(
         ))
     })?;
 
+    let device_info = build_device_info(
+        &headers,
+        &state.config.server.trusted_proxies,
+        &session_config.enhanced,
+    );
+
     // Validate SAML Response and create session
     let (session, return_to) = match authenticator
-        .exchange_response(&form.saml_response, &form.relay_state)
+        .exchange_response_with_device(&form.saml_response, &form.relay_state, device_info)
         .await
     {
         Ok(result) => result,
@@ -901,6 +985,24 @@ pub async fn saml_acs(
         "SAML session created"
     );
 
+    accept_invitations_on_sign_in(
+        &state,
+        &cookies,
+        &session,
+        ip_address.clone(),
+        user_agent.clone(),
+    )
+    .await;
+
+    enforce_session_limit_with_audit(
+        &state,
+        saml_registry.session_store().as_ref(),
+        &session,
+        &session_config.enhanced,
+        "saml",
+    )
+    .await;
+
     // Log successful authentication to audit log
     if let Some(services) = &state.services {
         let _ = services
@@ -1139,6 +1241,184 @@ pub async fn saml_metadata(
 // Helper Functions
 // ─────────────────────────────────────────────────────────────────────────────
 
+/// Build device info for a new session when device tracking is enabled.
+///
+/// The device ID is the first 16 hex chars of the SHA-256 of the User-Agent,
+/// so the same browser gets the same ID across logins.
+fn build_device_info(
+    headers: &axum::http::HeaderMap,
+    trusted_proxies: &TrustedProxiesConfig,
+    enhanced: &EnhancedSessionConfig,
+) -> Option<DeviceInfo> {
+    if !enhanced.enabled || !enhanced.track_devices {
+        return None;
+    }
+
+    let user_agent = headers
+        .get(axum::http::header::USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.to_string());
+
+    let device_id = user_agent.as_ref().map(|ua| {
+        use sha2::{Digest, Sha256};
+        let mut hasher = Sha256::new();
+        hasher.update(ua.as_bytes());
+        let hash = hasher.finalize();
+        hex::encode(&hash[..8])
+    });
+
+    let device_description = user_agent.as_ref().map(|ua| parse_user_agent(ua));
+
+    // ConnectInfo (direct TCP connection IP) is not available here; most
+    // production deployments use reverse proxies, so X-Forwarded-For is the
+    // primary source
+    let ip_address =
+        extract_client_ip_from_parts(headers, None, trusted_proxies).map(|ip| ip.to_string());
+
+    Some(DeviceInfo::new(
+        user_agent,
+        ip_address,
+        device_id,
+        device_description,
+    ))
+}
+
+/// Apply `max_concurrent_sessions` after a login and audit each session it
+/// evicts. Failures are logged but never fail the login.
+async fn enforce_session_limit_with_audit(
+    state: &AppState,
+    session_store: &dyn SessionStore,
+    session: &OidcSession,
+    enhanced: &EnhancedSessionConfig,
+    provider: &str,
+) {
+    if !enhanced.enabled || enhanced.max_concurrent_sessions == 0 {
+        return;
+    }
+
+    let evicted = match enforce_session_limit(
+        session_store,
+        &session.external_id,
+        enhanced.max_concurrent_sessions,
+    )
+    .await
+    {
+        Ok(evicted) => evicted,
+        Err(e) => {
+            tracing::warn!(
+                external_id = %session.external_id,
+                error = %e,
+                "Failed to enforce session limit"
+            );
+            return;
+        }
+    };
+
+    let Some(services) = &state.services else {
+        return;
+    };
+    for old in evicted {
+        let device = old.device.as_ref();
+        let _ = services
+            .audit_logs
+            .log_auth_event(AuthEventParams {
+                action: auth_events::SESSION_EVICTED,
+                session_id: old.id,
+                external_id: Some(&old.external_id),
+                email: old.email.as_deref(),
+                org_id: old.sso_org_id,
+                ip_address: device.and_then(|d| d.ip_address.clone()),
+                user_agent: device.and_then(|d| d.user_agent.clone()),
+                details: serde_json::json!({
+                    "provider": provider,
+                    "reason": "concurrent_session_limit",
+                    "max_concurrent_sessions": enhanced.max_concurrent_sessions,
+                    "replaced_by_session_id": session.id,
+                    "created_at": old.created_at,
+                    "last_activity": old.last_activity,
+                }),
+            })
+            .await;
+    }
+}
+
+/// Accept invitations for a user who just signed in: the one whose link
+/// they opened (from the invitation cookie) and pending invitations to the
+/// organization they signed in through. Failures are logged and never block
+/// the sign-in.
+async fn accept_invitations_on_sign_in(
+    state: &AppState,
+    cookies: &Cookies,
+    session: &OidcSession,
+    ip_address: Option<String>,
+    user_agent: Option<String>,
+) {
+    if !state.config.features.invitations.enabled {
+        return;
+    }
+    let Some(services) = &state.services else {
+        return;
+    };
+
+    let token = cookies
+        .get(INVITATION_COOKIE)
+        .map(|c| c.value().to_string());
+    if token.is_some() {
+        cookies.remove(Cookie::build(INVITATION_COOKIE).path("/auth").build());
+    }
+
+    let identity = crate::services::invitations::SignInIdentity {
+        external_id: &session.external_id,
+        email: session.email.as_deref(),
+        name: session.name.as_deref(),
+        sso_org_id: session.sso_org_id,
+    };
+    let accepted = match services
+        .invitations
+        .accept_on_sign_in(identity, token.as_deref())
+        .await
+    {
+        Ok(accepted) => accepted,
+        Err(e) => {
+            tracing::warn!(
+                external_id = %session.external_id,
+                error = %e,
+                "Failed to accept invitations on sign-in"
+            );
+            return;
+        }
+    };
+
+    for invitation in accepted {
+        tracing::info!(
+            invitation_id = %invitation.id,
+            org_id = %invitation.org_id,
+            user_id = ?invitation.accepted_by,
+            "Invitation accepted"
+        );
+        let _ = services
+            .audit_logs
+            .create(crate::models::CreateAuditLog {
+                actor_type: crate::models::AuditActorType::User,
+                actor_id: invitation.accepted_by,
+                action: "invitation.accept".to_string(),
+                resource_type: "invitation".to_string(),
+                resource_id: invitation.id,
+                org_id: Some(invitation.org_id),
+                project_id: None,
+                details: serde_json::json!({
+                    "email": invitation.email,
+                    "role": invitation.role,
+                    "team_id": invitation.team_id,
+                    "session_id": session.id,
+                }),
+                ip_address: ip_address.clone(),
+                user_agent: user_agent.clone(),
+            })
+            .await;
+    }
+}
+
 /// Parse a User-Agent string into a human-readable device description.
 ///
 /// This is a simple parser that extracts browser and OS information.
@@ -1301,6 +1581,8 @@ mod tests {
         matchers::{method, path},
     };
 
+    use crate::auth::session_store::{OidcSession, SessionStore};
+
     // =========================================================================
     // Test RSA Keypair (2048-bit, for testing only)
     // Generated with: openssl genrsa 2048
@@ -1420,6 +1702,16 @@ Q/y/GUsTXi5AiBMUhYFZu4vS
     /// Create a test app with per-org SSO configured to use the mock server
     /// Returns (app, org_slug) so tests can use ?org=<slug>
     async fn test_app_with_oidc(mock_server: &MockServer) -> (axum::Router, String) {
+        let (app, _, org_slug) = test_app_with_oidc_config(mock_server, "").await;
+        (app, org_slug)
+    }
+
+    /// Like [`test_app_with_oidc`], with `extra_config` appended to the
+    /// gateway config. Also returns the app state.
+    async fn test_app_with_oidc_config(
+        mock_server: &MockServer,
+        extra_config: &str,
+    ) -> (axum::Router, crate::AppState, String) {
         use std::sync::atomic::{AtomicU64, Ordering};
 
         use crate::models::{
@@ -1456,8 +1748,10 @@ cookie_name = "__test_session"
 
 [providers.test]
 type = "test"
+
+{}
 "#,
-            db_id
+            db_id, extra_config
         );
 
         let config =
@@ -1505,7 +1799,7 @@ type = "test"
             .await
             .expect("Failed to create SSO config");
 
-        (crate::build_app(&config, state), org_slug)
+        (crate::build_app(&config, state.clone()), state, org_slug)
     }
 
     /// Mount OIDC discovery endpoint on mock server
@@ -2675,4 +2969,238 @@ type = "test"
         assert!(log_entry.details.get("error").is_some());
         assert_eq!(log_entry.details["error"], "access_denied");
     }
+
+    // =========================================================================
+    // Enhanced Session Tests
+    // =========================================================================
+
+    const ENHANCED_SESSION_CONFIG: &str = r#"
+[cache]
+type = "memory"
+
+[auth.session.enhanced]
+enabled = true
+track_devices = true
+max_concurrent_sessions = 2
+activity_update_interval_secs = 0
+"#;
+
+    /// Store a session for `external_id` created `age` ago, bypassing login.
+    async fn seed_session(
+        state: &crate::AppState,
+        external_id: &str,
+        age: chrono::Duration,
+    ) -> uuid::Uuid {
+        let created_at = chrono::Utc::now() - age;
+        let session = OidcSession {
+            id: uuid::Uuid::new_v4(),
+            external_id: external_id.to_string(),
+            email: Some("test@example.com".to_string()),
+            name: Some("Test User".to_string()),
+            org: None,
+            groups: vec![],
+            roles: vec![],
+            access_token: None,
+            refresh_token: None,
+            created_at,
+            expires_at: chrono::Utc::now() + chrono::Duration::hours(1),
+            token_expires_at: None,
+            sso_org_id: None,
+            session_index: None,
+            device: None,
+            last_activity: Some(created_at),
+            claims: Default::default(),
+            passkey_verified_at: None,
+        };
+        let id = session.id;
+        state
+            .oidc_registry
+            .as_ref()
+            .unwrap()
+            .session_store()
+            .create_session(session)
+            .await
+            .unwrap();
+        id
+    }
+
+    /// Run the login and callback flow for "user-123" and return the new
+    /// session's ID from the session cookie.
+    async fn oidc_login(
+        app: &axum::Router,
+        mock_server: &MockServer,
+        org_slug: &str,
+    ) -> uuid::Uuid {
+        let login_request = Request::builder()
+            .method("GET")
+            .uri(format!("/auth/login?org={}", org_slug))
+            .body(Body::empty())
+            .unwrap();
+        let login_response = app.clone().oneshot(login_request).await.unwrap();
+        assert!(login_response.status().is_redirection());
+        let location = login_response
+            .headers()
+            .get("location")
+            .unwrap()
+            .to_str()
+            .unwrap();
+        let (state, nonce) = extract_auth_params(location);
+
+        let id_token = create_test_jwt_with_nonce(
+            &mock_server.uri(),
+            "user-123",
+            "test-client",
+            Some("test@example.com"),
+            Some("Test User"),
+            Some(&nonce),
+        );
+        mount_token_endpoint(mock_server, &id_token).await;
+
+        let callback_request = Request::builder()
+            .method("GET")
+            .uri(format!(
+                "/auth/callback?code=test_auth_code&state={}",
+                state
+            ))
+            .header(
+                "user-agent",
+                "Mozilla/5.0 (X11; Linux x86_64) Firefox/120.0",
+            )
+            .body(Body::empty())
+            .unwrap();
+        let callback_response = app.clone().oneshot(callback_request).await.unwrap();
+        assert!(callback_response.status().is_redirection());
+
+        let set_cookie = callback_response
+            .headers()
+            .get("set-cookie")
+            .expect("Missing set-cookie header")
+            .to_str()
+            .unwrap();
+        set_cookie
+            .strip_prefix("__test_session=")
+            .and_then(|rest| rest.split(';').next())
+            .expect("Session cookie not set")
+            .parse()
+            .expect("Session cookie is not a session ID")
+    }
+
+    #[tokio::test]
+    async fn test_callback_enforces_session_limit_with_audit() {
+        use crate::models::AuditLogQuery;
+
+        let mock_server = MockServer::start().await;
+        mount_oidc_discovery(&mock_server).await;
+        mount_jwks(&mock_server).await;
+
+        let (app, state, org_slug) =
+            test_app_with_oidc_config(&mock_server, ENHANCED_SESSION_CONFIG).await;
+
+        let oldest = seed_session(&state, "user-123", chrono::Duration::minutes(20)).await;
+        let older = seed_session(&state, "user-123", chrono::Duration::minutes(10)).await;
+
+        let new_session = oidc_login(&app, &mock_server, &org_slug).await;
+
+        // The third login evicts the oldest session
+        let store = state.oidc_registry.as_ref().unwrap().session_store();
+        let mut remaining: Vec<_> = store
+            .list_user_sessions("user-123")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|s| s.id)
+            .collect();
+        remaining.sort();
+        let mut expected = vec![older, new_session];
+        expected.sort();
+        assert_eq!(remaining, expected);
+
+        // The new session carries the device fingerprint
+        let session = store.get_session(new_session).await.unwrap().unwrap();
+        let device = session.device.expect("Device info should be tracked");
+        assert_eq!(
+            device.user_agent.as_deref(),
+            Some("Mozilla/5.0 (X11; Linux x86_64) Firefox/120.0")
+        );
+        assert!(device.device_id.is_some());
+
+        let audit_logs = state
+            .services
+            .as_ref()
+            .unwrap()
+            .audit_logs
+            .list(AuditLogQuery {
+                action: Some("auth.session.evicted".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(audit_logs.items.len(), 1);
+        assert_eq!(audit_logs.items[0].resource_id, oldest);
+        assert_eq!(
+            audit_logs.items[0].details["reason"],
+            "concurrent_session_limit"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_session_activity_and_org_force_logout() {
+        let mock_server = MockServer::start().await;
+        mount_oidc_discovery(&mock_server).await;
+        mount_jwks(&mock_server).await;
+
+        let (app, state, org_slug) =
+            test_app_with_oidc_config(&mock_server, ENHANCED_SESSION_CONFIG).await;
+
+        // A session not established through this org's SSO
+        seed_session(&state, "user-123", chrono::Duration::minutes(10)).await;
+
+        let session_id = oidc_login(&app, &mock_server, &org_slug).await;
+        let cookie = format!("__test_session={}", session_id);
+
+        // Backdate the session so the next request visibly refreshes it
+        let store = state.oidc_registry.as_ref().unwrap().session_store();
+        let mut session = store.get_session(session_id).await.unwrap().unwrap();
+        let stale = chrono::Utc::now() - chrono::Duration::minutes(5);
+        session.last_activity = Some(stale);
+        store.update_session(session).await.unwrap();
+
+        // The first authenticated request also JIT-provisions the member
+        let request = Request::builder()
+            .method("GET")
+            .uri("/auth/me")
+            .header("cookie", &cookie)
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let session = store.get_session(session_id).await.unwrap().unwrap();
+        assert!(session.last_activity.unwrap() > stale);
+
+        // A bare POST logs out every member, revoking all of their sessions
+        let request = Request::builder()
+            .method("POST")
+            .uri(format!("/admin/v1/organizations/{}/force-logout", org_slug))
+            .header("cookie", &cookie)
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["users_affected"], 1);
+        assert_eq!(body["sessions_revoked"], 2);
+        assert_eq!(body["enhanced_enabled"], true);
+
+        assert!(
+            store
+                .list_user_sessions("user-123")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
 }
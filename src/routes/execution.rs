@@ -26,8 +26,10 @@ use crate::{
     config::{ProviderConfig, SovereigntyMetadata, SovereigntyRequirements},
     observability::metrics,
     providers::{
-        FallbackDecision, Provider, ProviderError, anthropic, build_fallback_chain,
-        classify_provider_error, open_ai, should_fallback_on_response_status, test,
+        FallbackDecision, FallbackTarget, Provider, ProviderError, Tenant,
+        adaptive_concurrency::LoadSignal, anthropic, build_fallback_chain, classify_provider_error,
+        fair_queue, open_ai, response::limit_response_size, retry,
+        should_fallback_on_response_status, test,
     },
     services::{preprocess_file_search_tools, preprocess_web_search_tools},
 };
@@ -126,7 +128,8 @@ pub struct ExecutionResult {
 /// This trait abstracts the common operations needed for routing and execution:
 /// - Getting and setting the model name
 /// - Checking if streaming is enabled
-pub trait ApiPayload: Clone + Send + Sync + 'static {
+/// - Serializing for request size metrics
+pub trait ApiPayload: Clone + serde::Serialize + Send + Sync + 'static {
     /// Get the model name from the payload, if set.
     #[allow(dead_code)] // Required trait method for payload inspection
     fn model(&self) -> Option<&str>;
@@ -249,6 +252,58 @@ pub trait ProviderExecutor: Send + Sync + 'static {
     fn operation_name() -> &'static str;
 }
 
+/// Execute a request against a single provider, recording request/response
+/// payload sizes and enforcing the provider's `max_response_bytes` limit.
+///
+/// If the provider has a `fair_queue`, the request first waits for a slot on
+/// behalf of `tenant`, then for a slot under its `adaptive_concurrency` limit
+/// if it has one. Streaming responses hold both until they finish; the
+/// adaptive limit learns from the status and time to response headers.
+///
+/// The attempts sent to the provider, counting retries, are added to the
+/// response as [`retry::ATTEMPTS_HEADER`] and
+/// [`retry::TIMED_OUT_ATTEMPTS_HEADER`] for usage tracking.
+///
+/// Call sites that bypass [`execute_with_fallback`] should still go through
+/// this so size metrics, limits and fair queuing apply uniformly.
+pub async fn execute_provider<E: ProviderExecutor>(
+    state: &AppState,
+    provider_name: &str,
+    provider_config: &ProviderConfig,
+    payload: E::Payload,
+    tenant: &Tenant,
+) -> Result<Response, ProviderError> {
+    metrics::record_provider_request_payload(provider_name, &payload);
+    let queue_permit = match state
+        .fair_queues
+        .get_or_create(provider_name, provider_config.fair_queue_config())
+    {
+        Some(queue) => Some(queue.acquire(tenant).await?),
+        None => None,
+    };
+    let concurrency_permit = match state
+        .adaptive_limiters
+        .get_or_create(provider_name, provider_config.adaptive_concurrency_config())
+    {
+        Some(limiter) => Some(limiter.acquire().await?),
+        None => None,
+    };
+    let (mut result, attempts) =
+        retry::track_attempts(E::execute(state, provider_name, provider_config, payload)).await;
+    if let Ok(response) = &mut result {
+        attempts.insert_headers(response.headers_mut());
+    }
+    if let Some(permit) = &concurrency_permit {
+        permit.record(LoadSignal::from_result(&result, permit.sent_at().elapsed()));
+    }
+    let response =
+        limit_response_size(result?, provider_name, provider_config.max_response_bytes()).await?;
+    Ok(match (queue_permit, concurrency_permit) {
+        (None, None) => response,
+        permits => fair_queue::hold_permit(response, permits),
+    })
+}
+
 // ============================================================================
 // Executor Implementations
 // ============================================================================
@@ -401,6 +456,15 @@ impl ProviderExecutor for ResponsesExecutor {
             }
         }
 
+        // Gateway HTTP tools (`web_fetch` and `[[features.server_tools.http_tools]]`)
+        // are plain function tools, so the rewrite is the same for every provider.
+        #[cfg(feature = "server")]
+        let payload = {
+            let mut payload = payload;
+            crate::services::http_tool::preprocess_http_tools(&mut payload, &state.config.features);
+            payload
+        };
+
         match provider_config {
             ProviderConfig::OpenAi(config) => {
                 let mut payload = payload;
@@ -731,12 +795,14 @@ impl ProviderExecutor for EmbeddingExecutor {
 /// * `primary_provider_config` - Configuration for the primary provider
 /// * `primary_model_name` - Model name to use
 /// * `payload` - The API request payload
+/// * `sovereignty_requirements` - Constraints fallback providers must meet
+/// * `tenant` - Org/project the request is made for, used by fair queuing
 ///
 /// # Returns
 ///
 /// An `ExecutionResult` containing the response and provider metadata, or an `ApiError`.
 #[tracing::instrument(
-    skip(state, primary_provider_config, payload),
+    skip(state, primary_provider_config, payload, tenant),
     fields(
         operation = %E::operation_name(),
         primary_provider = %primary_provider_name,
@@ -753,14 +819,39 @@ pub async fn execute_with_fallback<E: ProviderExecutor>(
     primary_model_name: String,
     payload: E::Payload,
     sovereignty_requirements: Option<&SovereigntyRequirements>,
+    tenant: &Tenant,
 ) -> Result<ExecutionResult, ApiError> {
     // Build fallback chain
-    let fallback_chain = build_fallback_chain(
+    let mut fallback_chain = build_fallback_chain(
         &primary_provider_name,
         &primary_model_name,
         &state.config.providers,
     );
 
+    // Health-based routing: a degraded primary swaps places with its first
+    // eligible fallback for part of its traffic, so it's only tried last.
+    let (primary_provider_name, primary_provider_config, primary_model_name) =
+        match divert_from_degraded(
+            state,
+            &primary_provider_name,
+            &fallback_chain,
+            sovereignty_requirements,
+        ) {
+            Some((idx, config)) => {
+                let target = fallback_chain.remove(idx);
+                fallback_chain.push(FallbackTarget {
+                    provider_name: primary_provider_name,
+                    model_name: primary_model_name,
+                });
+                (target.provider_name, config, target.model_name)
+            }
+            None => (
+                primary_provider_name,
+                primary_provider_config,
+                primary_model_name,
+            ),
+        };
+
     // Track which provider we last tried (for metrics)
     let mut last_provider = primary_provider_name.clone();
     let mut last_model = primary_model_name.clone();
@@ -779,14 +870,81 @@ pub async fn execute_with_fallback<E: ProviderExecutor>(
     // Store the last response for chain exhaustion case
     let mut last_response: Option<Response> = None;
 
-    match E::execute(
+    // Index of the fallback that was raced against the primary, so the chain
+    // below doesn't try it a second time.
+    #[cfg_attr(target_arch = "wasm32", allow(unused_mut))]
+    let mut hedged: Option<usize> = None;
+
+    #[cfg(not(target_arch = "wasm32"))]
+    let primary_result = match hedge_target(
+        state,
+        &primary_provider_config,
+        &primary_model_name,
+        &fallback_chain,
+        sovereignty_requirements,
+    ) {
+        Some((delay, idx, hedge_config)) => {
+            let hedge = &fallback_chain[idx];
+            let mut hedge_payload = payload_for_fallbacks
+                .clone()
+                .expect("payload_for_fallbacks is Some when fallback_chain is non-empty");
+            hedge_payload.set_model(hedge.model_name.clone());
+
+            match execute_hedged::<E>(
+                state,
+                (&primary_provider_name, &primary_model_name),
+                &primary_provider_config,
+                current_payload,
+                hedge,
+                &hedge_config,
+                hedge_payload,
+                delay,
+                tenant,
+            )
+            .await
+            {
+                HedgeOutcome::NotSent(result) => result,
+                HedgeOutcome::Primary(result) => {
+                    hedged = Some(idx);
+                    last_provider = hedge.provider_name.clone();
+                    last_model = hedge.model_name.clone();
+                    result
+                }
+                HedgeOutcome::Hedge(response) => {
+                    tracing::Span::current().record("fallback_used", true);
+                    tracing::Span::current().record("final_provider", &hedge.provider_name);
+                    tracing::Span::current().record("final_model", &hedge.model_name);
+
+                    return Ok(ExecutionResult {
+                        response,
+                        provider_name: hedge.provider_name.clone(),
+                        model_name: hedge.model_name.clone(),
+                    });
+                }
+            }
+        }
+        None => {
+            execute_provider::<E>(
+                state,
+                &primary_provider_name,
+                &primary_provider_config,
+                current_payload,
+                tenant,
+            )
+            .await
+        }
+    };
+    #[cfg(target_arch = "wasm32")]
+    let primary_result = execute_provider::<E>(
         state,
         &primary_provider_name,
         &primary_provider_config,
         current_payload,
+        tenant,
     )
-    .await
-    {
+    .await;
+
+    match primary_result {
         Ok(response) => {
             // Check if response status should trigger fallback (5xx errors)
             let status = response.status();
@@ -838,64 +996,14 @@ pub async fn execute_with_fallback<E: ProviderExecutor>(
 
     for (idx, fallback) in fallback_chain.iter().enumerate() {
         let attempt = idx + 1;
-
-        // Get the fallback provider config
-        let Some(fallback_config) = state.config.providers.get(&fallback.provider_name) else {
-            tracing::warn!(
-                provider = %fallback.provider_name,
-                "Fallback provider not found, skipping"
-            );
-            continue;
-        };
-
-        // Re-check the circuit breaker right before we call this fallback.
-        // The chain was built once up front, but a provider may have tripped
-        // its breaker since then (often *because of* the failures that drove
-        // us into the fallback path). Skip provider+model combos whose breaker
-        // is open so we don't waste a hop poking a known-down upstream.
-        if let Some(breaker) = state.circuit_breakers.get(&fallback.provider_name)
-            && let Err(cb_err) = breaker.check()
-        {
-            tracing::info!(
-                provider = %fallback.provider_name,
-                model = %fallback.model_name,
-                error = %cb_err,
-                "Skipping fallback: circuit breaker is open"
-            );
+        if hedged == Some(idx) {
             continue;
         }
 
-        // Check sovereignty requirements for fallback provider/model
-        if let Some(reqs) = sovereignty_requirements {
-            let model_config = fallback_config.get_model_config(&fallback.model_name);
-            let provider_sov = fallback_config.sovereignty();
-            let model_sov = model_config.and_then(|mc| mc.sovereignty.as_ref());
-            let resolved = SovereigntyMetadata::merge(provider_sov, model_sov).unwrap_or_default();
-            let open_weights = model_config
-                .and_then(|mc| mc.open_weights)
-                .or_else(|| {
-                    let catalog_provider_id = crate::catalog::resolve_catalog_provider_id(
-                        fallback_config.provider_type_name(),
-                        fallback_config.base_url(),
-                        fallback_config.catalog_provider(),
-                    )?;
-                    state
-                        .model_catalog
-                        .lookup(&catalog_provider_id, &fallback.model_name)
-                        .map(|e| e.open_weights)
-                })
-                .unwrap_or(false);
-
-            if let Err(reason) = reqs.check(&resolved, open_weights) {
-                tracing::debug!(
-                    provider = %fallback.provider_name,
-                    model = %fallback.model_name,
-                    reason = %reason,
-                    "Fallback provider skipped due to sovereignty requirements"
-                );
-                continue;
-            }
-        }
+        let Some(fallback_config) = eligible_fallback(state, fallback, sovereignty_requirements)
+        else {
+            continue;
+        };
 
         // Update payload with fallback model
         let mut fallback_payload = payload_template.clone();
@@ -908,11 +1016,12 @@ pub async fn execute_with_fallback<E: ProviderExecutor>(
             "Trying fallback provider"
         );
 
-        match E::execute(
+        match execute_provider::<E>(
             state,
             &fallback.provider_name,
-            fallback_config,
+            &fallback_config,
             fallback_payload,
+            tenant,
         )
         .await
         {
@@ -1044,6 +1153,246 @@ pub async fn execute_with_fallback<E: ProviderExecutor>(
 // Helper Functions
 // ============================================================================
 
+/// Look up the config for a fallback target, with its runtime settings
+/// applied, or `None` if it should be skipped: the provider is missing or
+/// disabled, its circuit breaker is open, or it doesn't meet the request's
+/// sovereignty requirements.
+fn eligible_fallback(
+    state: &AppState,
+    fallback: &FallbackTarget,
+    sovereignty_requirements: Option<&SovereigntyRequirements>,
+) -> Option<ProviderConfig> {
+    let Some(file_config) = state.config.providers.get(&fallback.provider_name) else {
+        tracing::warn!(
+            provider = %fallback.provider_name,
+            "Fallback provider not found, skipping"
+        );
+        return None;
+    };
+    let Some(fallback_config) = state
+        .provider_settings
+        .apply(&fallback.provider_name, file_config)
+    else {
+        tracing::debug!(
+            provider = %fallback.provider_name,
+            "Skipping fallback: provider is disabled"
+        );
+        return None;
+    };
+
+    // Re-check the circuit breaker right before we call this fallback.
+    // The chain was built once up front, but a provider may have tripped
+    // its breaker since then (often *because of* the failures that drove
+    // us into the fallback path). Skip provider+model combos whose breaker
+    // is open so we don't waste a hop poking a known-down upstream.
+    if let Some(breaker) = state.circuit_breakers.get(&fallback.provider_name)
+        && let Err(cb_err) = breaker.check()
+    {
+        tracing::info!(
+            provider = %fallback.provider_name,
+            model = %fallback.model_name,
+            error = %cb_err,
+            "Skipping fallback: circuit breaker is open"
+        );
+        return None;
+    }
+
+    // Check sovereignty requirements for fallback provider/model
+    if let Some(reqs) = sovereignty_requirements {
+        let model_config = fallback_config.get_model_config(&fallback.model_name);
+        let provider_sov = fallback_config.sovereignty();
+        let model_sov = model_config.and_then(|mc| mc.sovereignty.as_ref());
+        let resolved = SovereigntyMetadata::merge(provider_sov, model_sov).unwrap_or_default();
+        let open_weights = model_config
+            .and_then(|mc| mc.open_weights)
+            .or_else(|| {
+                let catalog_provider_id = crate::catalog::resolve_catalog_provider_id(
+                    fallback_config.provider_type_name(),
+                    fallback_config.base_url(),
+                    fallback_config.catalog_provider(),
+                )?;
+                state
+                    .model_catalog
+                    .lookup(&catalog_provider_id, &fallback.model_name)
+                    .map(|e| e.open_weights)
+            })
+            .unwrap_or(false);
+
+        if let Err(reason) = reqs.check(&resolved, open_weights) {
+            tracing::debug!(
+                provider = %fallback.provider_name,
+                model = %fallback.model_name,
+                reason = %reason,
+                "Fallback provider skipped due to sovereignty requirements"
+            );
+            return None;
+        }
+    }
+
+    Some(fallback_config)
+}
+
+/// Outcome of racing the primary provider against a hedged request.
+#[cfg(not(target_arch = "wasm32"))]
+enum HedgeOutcome {
+    /// The primary finished within the hedge delay; no hedge was sent.
+    NotSent(Result<Response, ProviderError>),
+    /// The hedge was sent, and either the primary won or both failed (in
+    /// which case this is the primary's failure).
+    Primary(Result<Response, ProviderError>),
+    /// The hedge answered first.
+    Hedge(Response),
+}
+
+/// The fallback to send this request to instead of the primary, if the
+/// primary's health-based routing weight says to divert it.
+///
+/// A provider with weight `w` keeps a `w` share of its traffic. Only static
+/// providers are health checked, so dynamic providers are never diverted.
+fn divert_from_degraded(
+    state: &AppState,
+    primary_provider_name: &str,
+    fallback_chain: &[FallbackTarget],
+    sovereignty_requirements: Option<&SovereigntyRequirements>,
+) -> Option<(usize, ProviderConfig)> {
+    if fallback_chain.is_empty() || state.config.providers.get(primary_provider_name).is_none() {
+        return None;
+    }
+    let weight = state.provider_health.routing_weight(primary_provider_name);
+    if weight >= 1.0 || rand::random::<f64>() < weight {
+        return None;
+    }
+    let (idx, config) = fallback_chain
+        .iter()
+        .enumerate()
+        .find_map(|(idx, fallback)| {
+            eligible_fallback(state, fallback, sovereignty_requirements).map(|c| (idx, c))
+        })?;
+
+    let target = &fallback_chain[idx].provider_name;
+    tracing::debug!(
+        provider = %primary_provider_name,
+        target = %target,
+        weight,
+        "Diverting request from degraded provider"
+    );
+    metrics::record_provider_routing_diversion(primary_provider_name, target);
+    Some((idx, config))
+}
+
+/// The delay and fallback to hedge the primary with: the first eligible
+/// fallback, if the primary model has `hedge` configured.
+#[cfg(not(target_arch = "wasm32"))]
+fn hedge_target(
+    state: &AppState,
+    primary_provider_config: &ProviderConfig,
+    primary_model_name: &str,
+    fallback_chain: &[FallbackTarget],
+    sovereignty_requirements: Option<&SovereigntyRequirements>,
+) -> Option<(std::time::Duration, usize, ProviderConfig)> {
+    let hedge = primary_provider_config
+        .get_model_config(primary_model_name)?
+        .hedge
+        .as_ref()?;
+    fallback_chain
+        .iter()
+        .enumerate()
+        .find_map(|(idx, fallback)| {
+            eligible_fallback(state, fallback, sovereignty_requirements).map(|config| {
+                (
+                    std::time::Duration::from_millis(hedge.delay_ms),
+                    idx,
+                    config,
+                )
+            })
+        })
+}
+
+/// Send the request to the primary and, if it hasn't answered after `delay`,
+/// to the hedge target as well. The first usable response wins and the other
+/// request is cancelled by dropping it; a failure waits for the other side.
+#[cfg(not(target_arch = "wasm32"))]
+#[allow(clippy::too_many_arguments)]
+async fn execute_hedged<E: ProviderExecutor>(
+    state: &AppState,
+    (primary_provider_name, primary_model_name): (&str, &str),
+    primary_provider_config: &ProviderConfig,
+    primary_payload: E::Payload,
+    hedge: &FallbackTarget,
+    hedge_config: &ProviderConfig,
+    hedge_payload: E::Payload,
+    delay: std::time::Duration,
+    tenant: &Tenant,
+) -> HedgeOutcome {
+    let is_usable = |result: &Result<Response, ProviderError>| matches!(result, Ok(response) if !should_fallback_on_response_status(response.status()));
+    let record = |outcome: &str| {
+        metrics::record_hedged_request(
+            primary_provider_name,
+            primary_model_name,
+            &hedge.provider_name,
+            outcome,
+        )
+    };
+
+    let primary = execute_provider::<E>(
+        state,
+        primary_provider_name,
+        primary_provider_config,
+        primary_payload,
+        tenant,
+    );
+    tokio::pin!(primary);
+    tokio::select! {
+        result = &mut primary => {
+            record("not_sent");
+            return HedgeOutcome::NotSent(result);
+        }
+        _ = tokio::time::sleep(delay) => {}
+    }
+
+    tracing::debug!(
+        provider = %primary_provider_name,
+        model = %primary_model_name,
+        hedge_provider = %hedge.provider_name,
+        hedge_model = %hedge.model_name,
+        delay_ms = delay.as_millis() as u64,
+        "Primary provider is slow, sending hedged request"
+    );
+    let hedged = execute_provider::<E>(
+        state,
+        &hedge.provider_name,
+        hedge_config,
+        hedge_payload,
+        tenant,
+    );
+    tokio::pin!(hedged);
+
+    tokio::select! {
+        result = &mut primary => {
+            if is_usable(&result) {
+                record("primary");
+                return HedgeOutcome::Primary(result);
+            }
+            let hedge_result = hedged.await;
+            if is_usable(&hedge_result) {
+                record("hedge");
+                return HedgeOutcome::Hedge(hedge_result.expect("usable results are Ok"));
+            }
+            record("failed");
+            HedgeOutcome::Primary(result)
+        }
+        hedge_result = &mut hedged => {
+            if is_usable(&hedge_result) {
+                record("hedge");
+                return HedgeOutcome::Hedge(hedge_result.expect("usable results are Ok"));
+            }
+            let result = primary.await;
+            record(if is_usable(&result) { "primary" } else { "failed" });
+            HedgeOutcome::Primary(result)
+        }
+    }
+}
+
 /// Convert a provider error to an API error. The full error string is logged
 /// for operator debugging (it can contain internal URLs/paths from upstream
 /// SDKs) while only a generic message is returned to the client.
@@ -1082,6 +1431,12 @@ pub fn provider_error_to_api_error(e: ProviderError) -> ApiError {
             "circuit_breaker_open",
             cb.to_string(),
         ),
+        ProviderError::AtCapacity(e) => (e.status(), "provider_at_capacity", e.to_string()),
+        ProviderError::ConcurrencyLimited(e) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "provider_at_capacity",
+            e.to_string(),
+        ),
     };
 
     tracing::error!(error_code = %code, error = %e, "Provider error converted to API error");
@@ -1100,7 +1455,10 @@ mod tests {
         api_types::{Message, MessageContent},
         config::{GatewayConfig, ProvidersConfig},
         events::EventBus,
-        providers::CircuitBreakerRegistry,
+        providers::{
+            AdaptiveConcurrencyRegistry, CircuitBreakerRegistry, FairQueueRegistry,
+            ProviderSettingsRegistry,
+        },
     };
 
     /// Create a minimal AppState for testing with the given providers config.
@@ -1119,8 +1477,18 @@ mod tests {
             dlq: None,
             pricing: Arc::new(crate::pricing::PricingConfig::default()),
             circuit_breakers: CircuitBreakerRegistry::new(),
+            fair_queues: FairQueueRegistry::new(),
+            adaptive_limiters: AdaptiveConcurrencyRegistry::new(),
+            provider_settings: ProviderSettingsRegistry::new(
+                CircuitBreakerRegistry::new(),
+                FairQueueRegistry::new(),
+            ),
+            request_smoother: None,
+            token_buckets: None,
             provider_health: crate::jobs::ProviderHealthStateRegistry::new(),
+            job_scheduler: Arc::new(crate::jobs::JobScheduler::new(None)),
             task_tracker: tokio_util::task::TaskTracker::new(),
+            drain: Arc::new(crate::middleware::DrainState::new()),
             usage_drain: {
                 let tracker = tokio_util::task::TaskTracker::new();
                 crate::streaming::UsageDrainHandle::spawn(&tracker, 16)
@@ -1131,11 +1499,19 @@ mod tests {
             saml_registry: None,
             gateway_jwt_registry: None,
             policy_registry: None,
+            request_policies: None,
             usage_buffer: None,
             response_cache: None,
+            embeddings_cache: None,
             semantic_cache: None,
             input_guardrails: None,
             output_guardrails: None,
+            transforms: None,
+            shadow_traffic: None,
+            idempotency: None,
+            token_counter: None,
+            embedding_batcher: None,
+            email_notifier: None,
             event_bus: Arc::new(EventBus::new()),
             file_search_service: None,
             shell_runtime: None,
@@ -1233,6 +1609,7 @@ mod tests {
             "test-model".to_string(),
             make_chat_payload("test-model"),
             None,
+            &Tenant::default(),
         )
         .await;
 
@@ -1267,6 +1644,7 @@ mod tests {
             "test-model".to_string(),
             make_chat_payload("test-model"),
             None,
+            &Tenant::default(),
         )
         .await;
 
@@ -1299,6 +1677,7 @@ mod tests {
             "test-model".to_string(),
             make_chat_payload("test-model"),
             None,
+            &Tenant::default(),
         )
         .await;
 
@@ -1335,6 +1714,7 @@ mod tests {
             "test-model".to_string(),
             make_chat_payload("test-model"),
             None,
+            &Tenant::default(),
         )
         .await;
 
@@ -1377,6 +1757,7 @@ mod tests {
             "test-model".to_string(),
             make_chat_payload("test-model"),
             None,
+            &Tenant::default(),
         )
         .await;
 
@@ -1420,6 +1801,7 @@ mod tests {
             "test-model".to_string(),
             make_chat_payload("test-model"),
             None,
+            &Tenant::default(),
         )
         .await;
 
@@ -1454,6 +1836,7 @@ mod tests {
             "test-model".to_string(),
             make_chat_payload("test-model"),
             None,
+            &Tenant::default(),
         )
         .await;
 
@@ -1497,6 +1880,7 @@ mod tests {
             "test-model".to_string(),
             make_chat_payload("test-model"),
             None,
+            &Tenant::default(),
         )
         .await;
 
@@ -1532,6 +1916,7 @@ mod tests {
             "test-model".to_string(),
             make_chat_payload("test-model"),
             None,
+            &Tenant::default(),
         )
         .await;
 
@@ -1574,6 +1959,7 @@ mod tests {
             "gpt-4".to_string(),
             make_chat_payload("gpt-4"),
             None,
+            &Tenant::default(),
         )
         .await;
 
@@ -1615,6 +2001,7 @@ mod tests {
             "gpt-4".to_string(),
             make_chat_payload("gpt-4"),
             None,
+            &Tenant::default(),
         )
         .await;
 
@@ -1659,6 +2046,7 @@ mod tests {
             "gpt-4".to_string(),
             make_chat_payload("gpt-4"),
             None,
+            &Tenant::default(),
         )
         .await;
 
@@ -1701,6 +2089,7 @@ mod tests {
             "test-model".to_string(),
             make_chat_payload("test-model"),
             None,
+            &Tenant::default(),
         )
         .await;
 
@@ -1744,6 +2133,7 @@ mod tests {
             "test-model".to_string(),
             make_chat_payload("test-model"),
             None,
+            &Tenant::default(),
         )
         .await;
 
@@ -1752,4 +2142,117 @@ mod tests {
         assert_eq!(result.provider_name, "backup2");
         assert_eq!(result.response.status(), StatusCode::OK);
     }
+
+    // =========================================================================
+    // Test: Request hedging
+    // =========================================================================
+
+    #[tokio::test]
+    async fn test_hedge_wins_when_primary_is_slow() {
+        // Primary hangs for 5s before failing; the hedge is sent after 20ms
+        let providers = parse_providers(
+            r#"
+            [primary]
+            type = "test"
+            failure_mode = { type = "timeout", delay_ms = 5000 }
+            fallback_providers = ["backup"]
+
+            [primary.models."test-model".hedge]
+            delay_ms = 20
+
+            [backup]
+            type = "test"
+        "#,
+        );
+
+        let state = create_test_state(providers.clone());
+        let primary_config = providers.get("primary").unwrap().clone();
+
+        let start = std::time::Instant::now();
+        let result = execute_with_fallback::<ChatCompletionExecutor>(
+            &state,
+            "primary".to_string(),
+            primary_config,
+            "test-model".to_string(),
+            make_chat_payload("test-model"),
+            None,
+            &Tenant::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.provider_name, "backup");
+        assert_eq!(result.response.status(), StatusCode::OK);
+        assert!(start.elapsed() < std::time::Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_hedge_not_sent_when_primary_is_fast() {
+        let providers = parse_providers(
+            r#"
+            [primary]
+            type = "test"
+            fallback_providers = ["backup"]
+
+            [primary.models."test-model".hedge]
+            delay_ms = 1000
+
+            [backup]
+            type = "test"
+        "#,
+        );
+
+        let state = create_test_state(providers.clone());
+        let primary_config = providers.get("primary").unwrap().clone();
+
+        let result = execute_with_fallback::<ChatCompletionExecutor>(
+            &state,
+            "primary".to_string(),
+            primary_config,
+            "test-model".to_string(),
+            make_chat_payload("test-model"),
+            None,
+            &Tenant::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.provider_name, "primary");
+    }
+
+    #[tokio::test]
+    async fn test_primary_error_returned_when_hedge_also_fails() {
+        // The hedge fails fast, so the primary's (non-retryable) error decides
+        let providers = parse_providers(
+            r#"
+            [primary]
+            type = "test"
+            failure_mode = { type = "timeout", delay_ms = 100 }
+            fallback_providers = ["backup"]
+
+            [primary.models."test-model".hedge]
+            delay_ms = 10
+
+            [backup]
+            type = "test"
+            failure_mode = { type = "http_error", status_code = 503 }
+        "#,
+        );
+
+        let state = create_test_state(providers.clone());
+        let primary_config = providers.get("primary").unwrap().clone();
+
+        let result = execute_with_fallback::<ChatCompletionExecutor>(
+            &state,
+            "primary".to_string(),
+            primary_config,
+            "test-model".to_string(),
+            make_chat_payload("test-model"),
+            None,
+            &Tenant::default(),
+        )
+        .await;
+
+        assert!(result.is_err(), "Timeout error is not retryable");
+    }
 }
@@ -0,0 +1,140 @@
+//! Hub-side ingest endpoint for multi-gateway federation.
+//!
+//! Satellites authenticate with the per-satellite bearer token configured in
+//! `[[federation.hub.satellites]]`; regular API keys and sessions are not
+//! accepted here. Aggregated data is read back through the Admin API under
+//! `/admin/v1/federation`.
+
+use axum::{
+    Json,
+    extract::State,
+    http::{HeaderMap, StatusCode, header::AUTHORIZATION},
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+
+use crate::{AppState, models::FederationReport, openapi::ErrorResponse};
+
+/// Upper bound on usage rows per report. A satellite sends one row per model
+/// per day, so this leaves plenty of headroom for long lookback windows.
+const MAX_USAGE_ROWS: usize = 10_000;
+
+/// Errors returned to satellites.
+#[derive(Debug)]
+pub enum FederationIngestError {
+    /// This gateway isn't configured as a hub.
+    NotFound,
+    /// Missing or unknown bearer token.
+    Unauthorized,
+    /// Token is valid but belongs to a different satellite than the report.
+    GatewayMismatch,
+    /// Report failed validation.
+    BadRequest(String),
+    Internal,
+}
+
+impl IntoResponse for FederationIngestError {
+    fn into_response(self) -> Response {
+        let (status, code, message) = match self {
+            Self::NotFound => (
+                StatusCode::NOT_FOUND,
+                "not_found",
+                "Federation hub is not enabled".to_string(),
+            ),
+            Self::Unauthorized => (
+                StatusCode::UNAUTHORIZED,
+                "unauthorized",
+                "Missing or invalid federation token".to_string(),
+            ),
+            Self::GatewayMismatch => (
+                StatusCode::FORBIDDEN,
+                "forbidden",
+                "Report gateway_id does not match the token's satellite".to_string(),
+            ),
+            Self::BadRequest(message) => (StatusCode::BAD_REQUEST, "bad_request", message),
+            Self::Internal => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "internal_error",
+                "An internal error occurred".to_string(),
+            ),
+        };
+        (status, Json(ErrorResponse::new(code, message))).into_response()
+    }
+}
+
+/// Accept a usage and health report from a satellite gateway.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/federation/v1/reports",
+    tag = "federation",
+    operation_id = "federation_report",
+    request_body = FederationReport,
+    responses(
+        (status = 204, description = "Report stored"),
+        (status = 400, description = "Invalid report", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid federation token", body = ErrorResponse),
+        (status = 403, description = "Report gateway_id does not match the token", body = ErrorResponse),
+        (status = 404, description = "Federation hub is not enabled", body = ErrorResponse),
+    )
+))]
+pub async fn ingest_report(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(report): Json<FederationReport>,
+) -> Result<StatusCode, FederationIngestError> {
+    let hub = state
+        .config
+        .federation
+        .hub
+        .as_ref()
+        .ok_or(FederationIngestError::NotFound)?;
+
+    let token = headers
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .ok_or(FederationIngestError::Unauthorized)?;
+    let satellite = hub
+        .satellite_for_token(token)
+        .ok_or(FederationIngestError::Unauthorized)?;
+
+    if report.gateway_id != satellite.id {
+        tracing::warn!(
+            satellite = %satellite.id,
+            reported_gateway_id = %report.gateway_id,
+            "Rejected federation report with mismatched gateway_id"
+        );
+        return Err(FederationIngestError::GatewayMismatch);
+    }
+    if report.usage.len() > MAX_USAGE_ROWS {
+        return Err(FederationIngestError::BadRequest(format!(
+            "Report contains {} usage rows; the maximum is {MAX_USAGE_ROWS}",
+            report.usage.len()
+        )));
+    }
+
+    let services = state
+        .services
+        .as_ref()
+        .ok_or(FederationIngestError::Internal)?;
+    services
+        .federation
+        .record_report(&report, Utc::now())
+        .await
+        .map_err(|err| {
+            tracing::error!(
+                satellite = %satellite.id,
+                error = %err,
+                "Failed to store federation report"
+            );
+            FederationIngestError::Internal
+        })?;
+
+    tracing::debug!(
+        satellite = %satellite.id,
+        usage_rows = report.usage.len(),
+        providers = report.providers.len(),
+        "Stored federation report"
+    );
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -180,36 +180,272 @@ pub async fn liveness() -> impl IntoResponse {
     StatusCode::OK
 }
 
+/// Readiness probe response.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ReadinessStatus {
+    /// Whether the instance should receive traffic
+    #[cfg_attr(feature = "utoipa", schema(example = true))]
+    pub ready: bool,
+    /// Whether the instance is draining ahead of shutdown
+    #[cfg_attr(feature = "utoipa", schema(example = false))]
+    pub draining: bool,
+    /// Status of each dependency, critical ones first
+    pub dependencies: Vec<DependencyStatus>,
+}
+
+/// Status of a single dependency in the readiness response.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct DependencyStatus {
+    /// Dependency name ("database", "cache", "secrets", or the provider name)
+    #[cfg_attr(feature = "utoipa", schema(example = "database"))]
+    pub name: String,
+    /// Kind of dependency
+    pub kind: DependencyKind,
+    /// Current status
+    pub status: DependencyState,
+    /// Whether the instance is not ready while this dependency is down
+    #[cfg_attr(feature = "utoipa", schema(example = true))]
+    pub critical: bool,
+    /// Latency of the check in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "utoipa", schema(example = 5))]
+    pub latency_ms: Option<u64>,
+    /// Optional message with details
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Kind of a readiness dependency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyKind {
+    Database,
+    Cache,
+    Secrets,
+    Provider,
+}
+
+/// Status of a readiness dependency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyState {
+    /// Reachable and working
+    Up,
+    /// Working with reduced capacity (e.g. a half-open circuit breaker)
+    Degraded,
+    /// Unreachable or failing
+    Down,
+    /// Turned off at runtime
+    Disabled,
+    /// Not checked yet
+    Unknown,
+}
+
+impl DependencyStatus {
+    fn checked(
+        name: &str,
+        kind: DependencyKind,
+        critical: bool,
+        latency_ms: u64,
+        error: Option<String>,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            kind,
+            status: if error.is_none() {
+                DependencyState::Up
+            } else {
+                DependencyState::Down
+            },
+            critical,
+            latency_ms: Some(latency_ms),
+            message: error,
+        }
+    }
+}
+
+/// Status of a provider, from its runtime settings, circuit breaker and the
+/// latest background health check. Providers aren't checked on each probe,
+/// since that would send requests upstream every few seconds per replica.
+fn provider_status(state: &AppState, name: &str) -> DependencyStatus {
+    use crate::providers::{
+        circuit_breaker::CircuitState, health_check::HealthStatus, registry::endpoint_key,
+    };
+
+    let mut status = DependencyStatus {
+        name: name.to_string(),
+        kind: DependencyKind::Provider,
+        status: DependencyState::Unknown,
+        critical: false,
+        latency_ms: None,
+        message: None,
+    };
+    if !state.provider_settings.is_enabled(name) {
+        status.status = DependencyState::Disabled;
+        return status;
+    }
+
+    // Providers with several endpoints are down only when every endpoint is
+    let endpoints = state.circuit_breakers.endpoint_status(name);
+    if !endpoints.is_empty() {
+        let unavailable = endpoints
+            .iter()
+            .filter(|endpoint| {
+                let key = endpoint_key(name, endpoint.endpoint.as_deref().unwrap_or_default());
+                endpoint.state == CircuitState::Open
+                    || state
+                        .provider_health
+                        .get(&key)
+                        .is_some_and(|h| h.status == HealthStatus::Unhealthy)
+            })
+            .count();
+        status.latency_ms = endpoints.iter().filter_map(|e| e.latency_ms).min();
+        status.status = if unavailable == 0 {
+            DependencyState::Up
+        } else {
+            status.message = Some(format!(
+                "{unavailable} of {} endpoints unavailable",
+                endpoints.len()
+            ));
+            if unavailable == endpoints.len() {
+                DependencyState::Down
+            } else {
+                DependencyState::Degraded
+            }
+        };
+        return status;
+    }
+
+    let health = state.provider_health.get(name);
+    if let Some(health) = &health {
+        status.latency_ms = Some(health.latency_ms);
+        status.message = health.error.clone();
+    }
+
+    status.status = match state.circuit_breakers.status_for(name).map(|s| s.state) {
+        Some(CircuitState::Open) => {
+            status.message = Some("Circuit breaker open".to_string());
+            DependencyState::Down
+        }
+        Some(CircuitState::HalfOpen) => {
+            status.message = Some("Circuit breaker half-open".to_string());
+            DependencyState::Degraded
+        }
+        _ => match health.map(|h| h.status) {
+            Some(HealthStatus::Healthy) => DependencyState::Up,
+            Some(HealthStatus::Unhealthy) => DependencyState::Down,
+            Some(HealthStatus::Unknown) | None => DependencyState::Unknown,
+        },
+    };
+    status
+}
+
 /// Kubernetes readiness probe.
 ///
 /// Returns 200 if the service is ready to accept traffic. Checks that critical
-/// dependencies (database) are available. Use this for Kubernetes readiness
-/// probes to control traffic routing to pods.
+/// dependencies (database) are available and that the instance isn't
+/// draining. Use this for Kubernetes readiness probes to control traffic
+/// routing to pods.
+///
+/// The body lists each dependency with its status, so readiness gates and
+/// dashboards can tell why an instance isn't ready. Only critical
+/// dependencies affect the status code; the cache, secrets manager and
+/// providers are reported for information.
 #[cfg_attr(feature = "utoipa", utoipa::path(
     get,
     path = "/health/ready",
     tag = "health",
     operation_id = "health_readiness",
     responses(
-        (status = 200, description = "Service is ready to accept traffic"),
-        (status = 503, description = "Service is not ready (database unavailable)"),
+        (status = 200, description = "Service is ready to accept traffic", body = ReadinessStatus),
+        (status = 503, description = "Service is not ready (database unavailable or draining)", body = ReadinessStatus),
     )
 ))]
 #[tracing::instrument(name = "health.readiness", skip(state))]
 pub async fn readiness(State(state): State<AppState>) -> impl IntoResponse {
-    // In minimal mode (no database), always ready
-    if state.db.is_none() {
-        return StatusCode::OK;
+    // Draining instances take no new traffic
+    #[cfg(feature = "server")]
+    let draining = state.drain.is_draining();
+    #[cfg(not(feature = "server"))]
+    let draining = false;
+
+    let mut dependencies = Vec::new();
+
+    if let Some(db) = &state.db {
+        let start = std::time::Instant::now();
+        let result = db.health_check().await;
+        dependencies.push(DependencyStatus::checked(
+            "database",
+            DependencyKind::Database,
+            true,
+            start.elapsed().as_millis() as u64,
+            result
+                .err()
+                .map(|_| "Database connection failed".to_string()),
+        ));
     }
 
-    // Check database connectivity
-    if let Some(db) = &state.db
-        && db.health_check().await.is_err()
-    {
-        return StatusCode::SERVICE_UNAVAILABLE;
+    if let Some(cache) = &state.cache {
+        let start = std::time::Instant::now();
+        let result = cache.get_bytes("__health_check__").await;
+        dependencies.push(DependencyStatus::checked(
+            "cache",
+            DependencyKind::Cache,
+            false,
+            start.elapsed().as_millis() as u64,
+            result.err().map(|_| "Cache connection failed".to_string()),
+        ));
     }
 
-    StatusCode::OK
+    if let Some(secrets) = &state.secrets {
+        let start = std::time::Instant::now();
+        let result = secrets.health_check().await;
+        dependencies.push(DependencyStatus::checked(
+            "secrets",
+            DependencyKind::Secrets,
+            false,
+            start.elapsed().as_millis() as u64,
+            result
+                .err()
+                .map(|_| "Secrets manager unavailable".to_string()),
+        ));
+    }
+
+    let mut providers: Vec<_> = state
+        .config
+        .providers
+        .iter()
+        .map(|(name, _)| name)
+        .collect();
+    providers.sort_unstable();
+    dependencies.extend(
+        providers
+            .into_iter()
+            .map(|name| provider_status(&state, name)),
+    );
+
+    let ready = !draining
+        && dependencies
+            .iter()
+            .all(|d| !d.critical || d.status == DependencyState::Up);
+    let status_code = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (
+        status_code,
+        Json(ReadinessStatus {
+            ready,
+            draining,
+            dependencies,
+        }),
+    )
 }
 
 /// Prometheus metrics endpoint.
@@ -438,6 +674,44 @@ api_key = "sk-test-key"
         assert_eq!(status, StatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn test_readiness_reports_dependencies() {
+        let app = test_app_with_db().await;
+
+        let (status, body) = get_json(&app, "/health/ready").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["ready"], true);
+        assert_eq!(body["draining"], false);
+
+        let deps = body["dependencies"].as_array().unwrap();
+        let db = deps.iter().find(|d| d["name"] == "database").unwrap();
+        assert_eq!(db["kind"], "database");
+        assert_eq!(db["status"], "up");
+        assert_eq!(db["critical"], true);
+        assert!(db["latency_ms"].is_number());
+
+        // Providers are reported but never critical; with no health check
+        // run yet their status is unknown
+        let provider = deps.iter().find(|d| d["name"] == "test-openai").unwrap();
+        assert_eq!(provider["kind"], "provider");
+        assert_eq!(provider["status"], "unknown");
+        assert_eq!(provider["critical"], false);
+    }
+
+    #[tokio::test]
+    async fn test_readiness_no_db_reports_providers_only() {
+        let app = test_app_no_db().await;
+
+        let (status, body) = get_json(&app, "/health/ready").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["ready"], true);
+        let deps = body["dependencies"].as_array().unwrap();
+        assert!(deps.iter().all(|d| d["kind"] != "database"));
+        assert!(deps.iter().any(|d| d["name"] == "test-openai"));
+    }
+
     // ============================================================================
     // Metrics Endpoint Tests (/metrics)
     // ============================================================================
@@ -3,10 +3,13 @@ pub mod api;
 #[cfg(feature = "sso")]
 pub mod auth;
 pub mod execution;
+pub mod federation;
 pub mod health;
 pub mod oauth_public;
 #[cfg(feature = "sso")]
 pub mod scim;
+#[cfg(feature = "webauthn")]
+pub mod webauthn;
 #[cfg(feature = "server")]
 pub mod ws;
 
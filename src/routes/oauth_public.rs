@@ -15,7 +15,7 @@ use serde::Serialize;
 use crate::{
     AppState,
     config::ServerConfig,
-    models::{ApiKeyOwner, ApiKeyScope, CreateApiKey, ExchangeCodeForKey},
+    models::{ApiKeyHasher, ApiKeyOwner, ApiKeyScope, CreateApiKey, ExchangeCodeForKey},
     openapi::ErrorResponse,
     routes::admin::api_keys::{check_owner_create_limits, check_owner_membership_for_user},
     services::{OAuthPkceError, Services},
@@ -131,7 +131,9 @@ pub async fn token(
         }
     };
 
-    let prefix = state.config.auth.api_key_config().generation_prefix();
+    let api_key_config = state.config.auth.api_key_config();
+    let prefix = api_key_config.generation_prefix();
+    let hasher = ApiKeyHasher::from_config(api_key_config);
 
     let opts = stored.key_options.clone();
     let key_name = opts
@@ -165,9 +167,14 @@ pub async fn token(
     check_owner_membership_for_user(services, db, stored.user_id, &owner)
         .await
         .map_err(map_revalidation_error)?;
-    check_owner_create_limits(services, &owner, &state.config.limits.resource_limits)
-        .await
-        .map_err(map_revalidation_error)?;
+    check_owner_create_limits(
+        services,
+        &owner,
+        &state.config.limits.resource_limits,
+        &state.config.features.entitlements,
+    )
+    .await
+    .map_err(map_revalidation_error)?;
 
     let create_input = CreateApiKey {
         name: key_name,
@@ -185,7 +192,7 @@ pub async fn token(
 
     let created = services
         .api_keys
-        .create(create_input, &prefix)
+        .create(create_input, &prefix, &hasher)
         .await
         .map_err(|err| {
             tracing::error!(error = %err, "Failed to create OAuth-issued API key");
@@ -317,6 +324,8 @@ mod tests {
             cert_path: String::new(),
             key_path: String::new(),
             acknowledge_unsupported: true,
+            client_auth: None,
+            handshake_timeout_secs: 10,
         });
         ServerConfig {
             host: host.parse().unwrap(),
@@ -0,0 +1,772 @@
+//! Passkey (WebAuthn) routes for the admin UI.
+//!
+//! Each ceremony is a start/finish pair: `start` returns the browser's
+//! `navigator.credentials` options and a `challenge_id`, and `finish` takes
+//! the authenticator's response with that ID.
+//!
+//! - `/auth/webauthn/register/{start,finish}` - Add a passkey to the signed-in user
+//! - `/auth/webauthn/authenticate/{start,finish}` - Verify the current session
+//!   with a passkey, or sign in with one when there is no session
+//! - `/auth/webauthn/credentials` - List and remove the signed-in user's passkeys
+//!
+//! These routes resolve the session cookie themselves instead of using the
+//! admin auth middleware, so that `[auth.admin] passkeys = "required"` does
+//! not lock users out of the routes that satisfy it.
+
+use std::time::Duration;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use tower_cookies::{
+    Cookie, Cookies,
+    cookie::{SameSite as CookieSameSite, time::Duration as CookieDuration},
+};
+use uuid::Uuid;
+
+use super::auth::extract_client_ip_from_parts;
+use crate::{
+    AppState,
+    auth::{
+        AuthError,
+        session_store::{OidcSession, SharedSessionStore, validate_and_refresh_session},
+        webauthn::{
+            self, Ceremony, CreationChallengeResponse, DiscoverableKey, PublicKeyCredential,
+            RegisterPublicKeyCredential, RequestChallengeResponse, Webauthn,
+        },
+    },
+    config::{PasskeyPolicy, SameSite, WebAuthnConfig},
+    db::DbError,
+    models::{AdminPasskey, User},
+    services::{
+        Services,
+        audit_logs::{AuthEventParams, auth_events},
+    },
+};
+
+/// Request body for starting a passkey registration
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct RegisterStartRequest {
+    /// Display name for the new passkey, e.g. "YubiKey"
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+/// A started registration: pass `options` to `navigator.credentials.create()`
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct RegisterStartResponse {
+    pub challenge_id: String,
+    #[cfg_attr(feature = "utoipa", schema(value_type = Object))]
+    pub options: CreationChallengeResponse,
+}
+
+/// The authenticator's response to a registration challenge
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct RegisterFinishRequest {
+    pub challenge_id: String,
+    /// Display name for the new passkey; overrides the one given at start
+    #[serde(default)]
+    pub name: Option<String>,
+    #[cfg_attr(feature = "utoipa", schema(value_type = Object))]
+    pub credential: RegisterPublicKeyCredential,
+}
+
+/// A started authentication: pass `options` to `navigator.credentials.get()`
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct AuthenticateStartResponse {
+    pub challenge_id: String,
+    #[cfg_attr(feature = "utoipa", schema(value_type = Object))]
+    pub options: RequestChallengeResponse,
+}
+
+/// The authenticator's response to an authentication challenge
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct AuthenticateFinishRequest {
+    pub challenge_id: String,
+    #[cfg_attr(feature = "utoipa", schema(value_type = Object))]
+    pub credential: PublicKeyCredential,
+}
+
+/// Result of a successful passkey authentication
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct AuthenticateFinishResponse {
+    pub user_id: Uuid,
+    /// ID of the passkey that was used
+    pub passkey_id: Uuid,
+    /// Whether a new session was created (passkey sign-in) rather than the
+    /// current one verified
+    pub signed_in: bool,
+}
+
+/// Registered passkeys of the signed-in user
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct PasskeyListResponse {
+    pub data: Vec<AdminPasskey>,
+}
+
+struct Context<'a> {
+    config: &'a WebAuthnConfig,
+    relying_party: Webauthn,
+    services: &'a Services,
+    cache: &'a dyn crate::cache::Cache,
+    sessions: SharedSessionStore,
+}
+
+fn context(state: &AppState) -> Result<Context<'_>, AuthError> {
+    let admin = &state.config.auth.admin;
+    let config = match (&admin.webauthn, admin.passkeys) {
+        (Some(config), PasskeyPolicy::Optional | PasskeyPolicy::Required) => config,
+        _ => return Err(AuthError::Forbidden("Passkeys are not enabled".to_string())),
+    };
+    let relying_party = webauthn::relying_party(config)
+        .map_err(|e| AuthError::Internal(format!("Invalid WebAuthn configuration: {e}")))?;
+    let services = state
+        .services
+        .as_ref()
+        .ok_or_else(|| AuthError::Internal("Passkeys require a database".to_string()))?;
+    let cache = state
+        .cache
+        .as_deref()
+        .ok_or_else(|| AuthError::Internal("Passkeys require a cache".to_string()))?;
+    let sessions = session_store(state)
+        .ok_or_else(|| AuthError::Internal("Passkeys require a session store".to_string()))?;
+
+    Ok(Context {
+        config,
+        relying_party,
+        services,
+        cache,
+        sessions,
+    })
+}
+
+fn session_store(state: &AppState) -> Option<SharedSessionStore> {
+    if let Some(registry) = &state.oidc_registry {
+        return Some(registry.session_store().clone());
+    }
+    #[cfg(feature = "saml")]
+    if let Some(registry) = &state.saml_registry {
+        return Some(registry.session_store().clone());
+    }
+    None
+}
+
+fn db_error(e: DbError) -> AuthError {
+    AuthError::Internal(e.to_string())
+}
+
+fn rejected(e: webauthn::WebauthnError) -> AuthError {
+    tracing::debug!(error = %e, "WebAuthn ceremony rejected");
+    AuthError::InvalidCredentials
+}
+
+/// The valid session in the request's cookie and its user.
+async fn current_session(
+    state: &AppState,
+    ctx: &Context<'_>,
+    cookies: &Cookies,
+) -> Result<Option<(OidcSession, User)>, AuthError> {
+    let session_config = state.config.auth.session_config_or_default();
+    let Some(session_id) = cookies
+        .get(&session_config.cookie_name)
+        .and_then(|c| c.value().parse::<Uuid>().ok())
+    else {
+        return Ok(None);
+    };
+    let Ok(session) =
+        validate_and_refresh_session(ctx.sessions.as_ref(), session_id, &session_config.enhanced)
+            .await
+    else {
+        return Ok(None);
+    };
+    let user = ctx
+        .services
+        .users
+        .get_by_external_id(&session.external_id)
+        .await
+        .map_err(db_error)?;
+    Ok(user.map(|user| (session, user)))
+}
+
+async fn require_session(
+    state: &AppState,
+    ctx: &Context<'_>,
+    cookies: &Cookies,
+) -> Result<(OidcSession, User), AuthError> {
+    current_session(state, ctx, cookies)
+        .await?
+        .ok_or(AuthError::MissingCredentials)
+}
+
+async fn mark_verified(ctx: &Context<'_>, mut session: OidcSession) -> Result<(), AuthError> {
+    session.passkey_verified_at = Some(Utc::now());
+    ctx.sessions
+        .update_session(session)
+        .await
+        .map_err(|e| AuthError::Internal(format!("Failed to update session: {e}")))
+}
+
+fn client_details(
+    state: &AppState,
+    headers: &axum::http::HeaderMap,
+) -> (Option<String>, Option<String>) {
+    let ip_address =
+        extract_client_ip_from_parts(headers, None, &state.config.server.trusted_proxies)
+            .map(|ip| ip.to_string());
+    let user_agent = headers
+        .get(axum::http::header::USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.to_string());
+    (ip_address, user_agent)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn audit(
+    ctx: &Context<'_>,
+    state: &AppState,
+    headers: &axum::http::HeaderMap,
+    action: &str,
+    session_id: Uuid,
+    user: Option<&User>,
+    org_id: Option<Uuid>,
+    details: serde_json::Value,
+) {
+    let (ip_address, user_agent) = client_details(state, headers);
+    let _ = ctx
+        .services
+        .audit_logs
+        .log_auth_event(AuthEventParams {
+            action,
+            session_id,
+            external_id: user.map(|u| u.external_id.as_str()),
+            email: user.and_then(|u| u.email.as_deref()),
+            org_id,
+            ip_address,
+            user_agent,
+            details,
+        })
+        .await;
+}
+
+fn ttl(config: &WebAuthnConfig) -> Duration {
+    Duration::from_secs(config.challenge_ttl_secs)
+}
+
+/// Start passkey registration
+///
+/// Requires a session. Under `passkeys = "required"`, a user who already has
+/// passkeys must verify the session with one before adding another.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/auth/webauthn/register/start",
+    tag = "auth",
+    operation_id = "webauthn_register_start",
+    request_body = RegisterStartRequest,
+    responses(
+        (status = 200, description = "Registration started", body = RegisterStartResponse),
+        (status = 401, description = "Not signed in", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Passkey verification required", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "auth.webauthn.register_start", skip_all)]
+pub async fn register_start(
+    State(state): State<AppState>,
+    cookies: Cookies,
+    body: Option<Json<RegisterStartRequest>>,
+) -> Result<Json<RegisterStartResponse>, AuthError> {
+    let ctx = context(&state)?;
+    let (session, user) = require_session(&state, &ctx, &cookies).await?;
+
+    let existing = ctx
+        .services
+        .passkeys
+        .list(user.id)
+        .await
+        .map_err(db_error)?;
+    if state.config.auth.admin.passkeys == PasskeyPolicy::Required
+        && !existing.is_empty()
+        && session.passkey_verified_at.is_none()
+    {
+        return Err(AuthError::PasskeyRequired { registered: true });
+    }
+
+    let exclude = existing
+        .iter()
+        .map(|p| webauthn::deserialize_passkey(&p.credential).map(|k| k.cred_id().clone()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let user_name = user.email.as_deref().unwrap_or(&user.external_id);
+    let display_name = user.name.as_deref().unwrap_or(user_name);
+
+    let (options, registration) = ctx
+        .relying_party
+        .start_passkey_registration(user.id, user_name, display_name, Some(exclude))
+        .map_err(|e| AuthError::Internal(format!("Failed to start registration: {e}")))?;
+
+    let name = body.and_then(|Json(b)| b.name);
+    let ceremony = Ceremony::Registration {
+        user_id: user.id,
+        session_id: session.id,
+        name,
+        state: registration,
+    };
+    let challenge_id = webauthn::store_ceremony(ctx.cache, &ceremony, ttl(ctx.config)).await?;
+
+    Ok(Json(RegisterStartResponse {
+        challenge_id,
+        options,
+    }))
+}
+
+/// Finish passkey registration
+///
+/// Stores the new passkey and marks the current session as passkey-verified.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/auth/webauthn/register/finish",
+    tag = "auth",
+    operation_id = "webauthn_register_finish",
+    request_body = RegisterFinishRequest,
+    responses(
+        (status = 201, description = "Passkey registered", body = AdminPasskey),
+        (status = 401, description = "Invalid or expired challenge", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "auth.webauthn.register_finish", skip_all)]
+pub async fn register_finish(
+    State(state): State<AppState>,
+    cookies: Cookies,
+    headers: axum::http::HeaderMap,
+    Json(body): Json<RegisterFinishRequest>,
+) -> Result<(StatusCode, Json<AdminPasskey>), AuthError> {
+    let ctx = context(&state)?;
+    let (session, user) = require_session(&state, &ctx, &cookies).await?;
+
+    let Some(Ceremony::Registration {
+        user_id,
+        session_id,
+        name,
+        state: registration,
+    }) = webauthn::take_ceremony(ctx.cache, &body.challenge_id).await?
+    else {
+        return Err(AuthError::InvalidCredentials);
+    };
+    if user_id != user.id || session_id != session.id {
+        return Err(AuthError::InvalidCredentials);
+    }
+
+    let passkey = ctx
+        .relying_party
+        .finish_passkey_registration(&body.credential, &registration)
+        .map_err(rejected)?;
+
+    let stored = ctx
+        .services
+        .passkeys
+        .register(
+            user.id,
+            webauthn::encode_credential_id(passkey.cred_id()),
+            body.name.or(name).as_deref(),
+            webauthn::serialize_passkey(&passkey)?,
+        )
+        .await
+        .map_err(|e| match e {
+            DbError::Conflict(msg) => AuthError::Forbidden(msg),
+            e => db_error(e),
+        })?;
+
+    let org_id = session.sso_org_id;
+    mark_verified(&ctx, session.clone()).await?;
+
+    audit(
+        &ctx,
+        &state,
+        &headers,
+        auth_events::PASSKEY_REGISTERED,
+        session.id,
+        Some(&user),
+        org_id,
+        serde_json::json!({ "passkey_id": stored.id, "name": stored.name }),
+    )
+    .await;
+
+    Ok((StatusCode::CREATED, Json(stored)))
+}
+
+/// Start passkey authentication
+///
+/// With a session, challenges one of the user's passkeys to verify it. Without
+/// one, starts a passkey sign-in (unless `passkey_login = false`).
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/auth/webauthn/authenticate/start",
+    tag = "auth",
+    operation_id = "webauthn_authenticate_start",
+    responses(
+        (status = 200, description = "Authentication started", body = AuthenticateStartResponse),
+        (status = 401, description = "Not signed in and passkey sign-in is disabled", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "No passkey registered", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "auth.webauthn.authenticate_start", skip_all)]
+pub async fn authenticate_start(
+    State(state): State<AppState>,
+    cookies: Cookies,
+) -> Result<Json<AuthenticateStartResponse>, AuthError> {
+    let ctx = context(&state)?;
+
+    let (options, ceremony) = match current_session(&state, &ctx, &cookies).await? {
+        Some((session, user)) => {
+            let passkeys = ctx
+                .services
+                .passkeys
+                .list(user.id)
+                .await
+                .map_err(db_error)?
+                .iter()
+                .map(|p| webauthn::deserialize_passkey(&p.credential))
+                .collect::<Result<Vec<_>, _>>()?;
+            if passkeys.is_empty() {
+                return Err(AuthError::PasskeyRequired { registered: false });
+            }
+            let (options, authentication) = ctx
+                .relying_party
+                .start_passkey_authentication(&passkeys)
+                .map_err(|e| AuthError::Internal(format!("Failed to start authentication: {e}")))?;
+            let ceremony = Ceremony::SecondFactor {
+                user_id: user.id,
+                session_id: session.id,
+                state: authentication,
+            };
+            (options, ceremony)
+        }
+        None if state.config.auth.admin.passkey_login => {
+            let (options, authentication) = ctx
+                .relying_party
+                .start_discoverable_authentication()
+                .map_err(|e| {
+                AuthError::Internal(format!("Failed to start authentication: {e}"))
+            })?;
+            (
+                options,
+                Ceremony::Login {
+                    state: authentication,
+                },
+            )
+        }
+        None => return Err(AuthError::MissingCredentials),
+    };
+
+    let challenge_id = webauthn::store_ceremony(ctx.cache, &ceremony, ttl(ctx.config)).await?;
+    Ok(Json(AuthenticateStartResponse {
+        challenge_id,
+        options,
+    }))
+}
+
+/// Finish passkey authentication
+///
+/// Marks the current session as passkey-verified, or for a passkey sign-in
+/// creates a new session and sets its cookie.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = "/auth/webauthn/authenticate/finish",
+    tag = "auth",
+    operation_id = "webauthn_authenticate_finish",
+    request_body = AuthenticateFinishRequest,
+    responses(
+        (status = 200, description = "Passkey verified", body = AuthenticateFinishResponse),
+        (status = 401, description = "Invalid passkey or expired challenge", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "auth.webauthn.authenticate_finish", skip_all)]
+pub async fn authenticate_finish(
+    State(state): State<AppState>,
+    cookies: Cookies,
+    headers: axum::http::HeaderMap,
+    Json(body): Json<AuthenticateFinishRequest>,
+) -> Result<Json<AuthenticateFinishResponse>, AuthError> {
+    let ctx = context(&state)?;
+    let Some(ceremony) = webauthn::take_ceremony(ctx.cache, &body.challenge_id).await? else {
+        return Err(AuthError::InvalidCredentials);
+    };
+
+    let credential_id = webauthn::encode_credential_id(&body.credential.raw_id);
+    let stored = ctx
+        .services
+        .passkeys
+        .get_by_credential_id(&credential_id)
+        .await
+        .map_err(db_error)?;
+
+    let result = match (&ceremony, &stored) {
+        (Ceremony::SecondFactor { user_id, state, .. }, Some(stored))
+            if stored.user_id == *user_id =>
+        {
+            ctx.relying_party
+                .finish_passkey_authentication(&body.credential, state)
+        }
+        (Ceremony::Login { state }, Some(stored)) => {
+            let passkey = webauthn::deserialize_passkey(&stored.credential)?;
+            ctx.relying_party.finish_discoverable_authentication(
+                &body.credential,
+                state.clone(),
+                &[DiscoverableKey::from(&passkey)],
+            )
+        }
+        _ => Err(webauthn::WebauthnError::CredentialNotFound),
+    };
+
+    let (result, stored) = match (result, stored) {
+        (Ok(result), Some(stored)) => (result, stored),
+        (result, stored) => {
+            if let Err(e) = result {
+                tracing::debug!(error = %e, "Passkey authentication rejected");
+            }
+            let user = match &stored {
+                Some(s) => ctx.services.users.get_by_id(s.user_id).await.ok().flatten(),
+                None => None,
+            };
+            audit(
+                &ctx,
+                &state,
+                &headers,
+                auth_events::PASSKEY_FAILED,
+                Uuid::nil(),
+                user.as_ref(),
+                None,
+                serde_json::json!({ "credential_id": credential_id }),
+            )
+            .await;
+            return Err(AuthError::InvalidCredentials);
+        }
+    };
+
+    // Persist the advanced signature counter / backup state
+    let mut passkey = webauthn::deserialize_passkey(&stored.credential)?;
+    passkey.update_credential(&result);
+    ctx.services
+        .passkeys
+        .record_use(stored.id, &webauthn::serialize_passkey(&passkey)?)
+        .await
+        .map_err(db_error)?;
+
+    let user = ctx
+        .services
+        .users
+        .get_by_id(stored.user_id)
+        .await
+        .map_err(db_error)?
+        .ok_or(AuthError::InvalidCredentials)?;
+
+    match ceremony {
+        Ceremony::SecondFactor { session_id, .. } => {
+            let session = ctx
+                .sessions
+                .get_session(session_id)
+                .await
+                .map_err(|e| AuthError::Internal(format!("Failed to load session: {e}")))?
+                .filter(|s| s.external_id == user.external_id)
+                .ok_or(AuthError::SessionNotFound)?;
+            let org_id = session.sso_org_id;
+            mark_verified(&ctx, session).await?;
+            audit(
+                &ctx,
+                &state,
+                &headers,
+                auth_events::PASSKEY_VERIFIED,
+                session_id,
+                Some(&user),
+                org_id,
+                serde_json::json!({ "passkey_id": stored.id }),
+            )
+            .await;
+
+            Ok(Json(AuthenticateFinishResponse {
+                user_id: user.id,
+                passkey_id: stored.id,
+                signed_in: false,
+            }))
+        }
+        Ceremony::Login { .. } => {
+            let session = create_session(&state, &ctx, &cookies, &headers, &user).await?;
+            audit(
+                &ctx,
+                &state,
+                &headers,
+                auth_events::PASSKEY_LOGIN,
+                session.id,
+                Some(&user),
+                None,
+                serde_json::json!({ "provider": "passkey", "passkey_id": stored.id }),
+            )
+            .await;
+
+            Ok(Json(AuthenticateFinishResponse {
+                user_id: user.id,
+                passkey_id: stored.id,
+                signed_in: true,
+            }))
+        }
+        Ceremony::Registration { .. } => Err(AuthError::InvalidCredentials),
+    }
+}
+
+/// Create a passkey sign-in session and set its cookie.
+///
+/// The session carries no IdP groups, roles, or SSO organization: access
+/// comes from the user's memberships alone, and orgs that require SSO
+/// still reject it.
+async fn create_session(
+    state: &AppState,
+    ctx: &Context<'_>,
+    cookies: &Cookies,
+    headers: &axum::http::HeaderMap,
+    user: &User,
+) -> Result<OidcSession, AuthError> {
+    let session_config = state.config.auth.session_config_or_default();
+    let now = Utc::now();
+    let (ip_address, user_agent) = client_details(state, headers);
+    let device = (session_config.enhanced.enabled && session_config.enhanced.track_devices)
+        .then(|| crate::auth::session_store::DeviceInfo::new(user_agent, ip_address, None, None));
+
+    let session = OidcSession {
+        id: Uuid::new_v4(),
+        external_id: user.external_id.clone(),
+        email: user.email.clone(),
+        name: user.name.clone(),
+        org: None,
+        groups: Vec::new(),
+        roles: Vec::new(),
+        access_token: None,
+        refresh_token: None,
+        created_at: now,
+        expires_at: now + chrono::Duration::seconds(session_config.duration_secs as i64),
+        token_expires_at: None,
+        sso_org_id: None,
+        session_index: None,
+        device,
+        last_activity: Some(now),
+        claims: Default::default(),
+        passkey_verified_at: Some(now),
+    };
+    ctx.sessions
+        .create_session(session.clone())
+        .await
+        .map_err(|e| AuthError::Internal(format!("Failed to store session: {e}")))?;
+
+    let same_site = match session_config.same_site {
+        SameSite::Strict => CookieSameSite::Strict,
+        SameSite::Lax => CookieSameSite::Lax,
+        SameSite::None => CookieSameSite::None,
+    };
+    cookies.add(
+        Cookie::build((session_config.cookie_name.clone(), session.id.to_string()))
+            .path("/")
+            .http_only(true)
+            .secure(session_config.secure)
+            .same_site(same_site)
+            .max_age(CookieDuration::seconds(session_config.duration_secs as i64))
+            .build(),
+    );
+
+    tracing::info!(
+        session_id = %session.id,
+        external_id = %session.external_id,
+        "Passkey session created"
+    );
+    Ok(session)
+}
+
+/// List the signed-in user's passkeys
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = "/auth/webauthn/credentials",
+    tag = "auth",
+    operation_id = "webauthn_credentials_list",
+    responses(
+        (status = 200, description = "Registered passkeys", body = PasskeyListResponse),
+        (status = 401, description = "Not signed in", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "auth.webauthn.credentials_list", skip_all)]
+pub async fn list_credentials(
+    State(state): State<AppState>,
+    cookies: Cookies,
+) -> Result<Json<PasskeyListResponse>, AuthError> {
+    let ctx = context(&state)?;
+    let (_, user) = require_session(&state, &ctx, &cookies).await?;
+    let data = ctx
+        .services
+        .passkeys
+        .list(user.id)
+        .await
+        .map_err(db_error)?;
+    Ok(Json(PasskeyListResponse { data }))
+}
+
+/// Remove one of the signed-in user's passkeys
+///
+/// Under `passkeys = "required"` the session must be passkey-verified.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    delete,
+    path = "/auth/webauthn/credentials/{id}",
+    tag = "auth",
+    operation_id = "webauthn_credentials_delete",
+    params(("id" = Uuid, Path, description = "Passkey ID")),
+    responses(
+        (status = 204, description = "Passkey removed"),
+        (status = 401, description = "Not signed in", body = crate::openapi::ErrorResponse),
+        (status = 403, description = "Passkey verification required", body = crate::openapi::ErrorResponse),
+        (status = 404, description = "Passkey not found", body = crate::openapi::ErrorResponse),
+    )
+))]
+#[tracing::instrument(name = "auth.webauthn.credentials_delete", skip_all, fields(%id))]
+pub async fn delete_credential(
+    State(state): State<AppState>,
+    cookies: Cookies,
+    headers: axum::http::HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AuthError> {
+    let ctx = context(&state)?;
+    let (session, user) = require_session(&state, &ctx, &cookies).await?;
+    if state.config.auth.admin.passkeys == PasskeyPolicy::Required
+        && session.passkey_verified_at.is_none()
+    {
+        return Err(AuthError::PasskeyRequired { registered: true });
+    }
+
+    if !ctx
+        .services
+        .passkeys
+        .delete(user.id, id)
+        .await
+        .map_err(db_error)?
+    {
+        return Ok(StatusCode::NOT_FOUND);
+    }
+
+    audit(
+        &ctx,
+        &state,
+        &headers,
+        auth_events::PASSKEY_REMOVED,
+        session.id,
+        Some(&user),
+        session.sso_org_id,
+        serde_json::json!({ "passkey_id": id }),
+    )
+    .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -23,6 +23,7 @@
 //! - `health` - Provider health and circuit breaker events
 //! - `budget` - Budget threshold events
 //! - `rate_limit` - Rate limit warning events
+//! - `requests` - API request started/completed summaries
 //! - `all` - All events (wildcard)
 //!
 //! To unsubscribe:
@@ -57,7 +58,8 @@ use crate::{
     cache::CacheKeys,
     config::WebSocketConfig,
     events::{EventTopic, ServerEvent},
-    models::{CachedApiKey, has_valid_prefix, hash_api_key},
+    middleware::util::api_key::{FoundApiKey, find_api_key},
+    models::{ApiKeyHasher, CachedApiKey, has_valid_prefix},
 };
 
 /// Query parameters for WebSocket connection.
@@ -203,7 +205,8 @@ async fn authenticate_with_api_key(
         return Err(AuthError::InvalidApiKeyFormat);
     }
 
-    let key_hash = hash_api_key(token);
+    let hasher = ApiKeyHasher::from_config(state.config.auth.api_key_config());
+    let key_hash = hasher.lookup_hash(token);
 
     // Check cache first
     if let Some(cache) = &state.cache {
@@ -247,9 +250,10 @@ async fn authenticate_with_api_key(
 
     // Fall back to database lookup
     if let Some(db) = &state.db
-        && let Some(key_with_owner) = db
-            .api_keys()
-            .get_by_hash(&key_hash)
+        && let Some(FoundApiKey {
+            key: key_with_owner,
+            ..
+        }) = find_api_key(db, &hasher, token)
             .await
             .map_err(|e| AuthError::Internal(e.to_string()))?
     {
@@ -375,6 +379,8 @@ fn parse_topic(s: &str) -> Option<EventTopic> {
         "health" => Some(EventTopic::Health),
         "budget" => Some(EventTopic::Budget),
         "rate_limit" | "ratelimit" => Some(EventTopic::RateLimit),
+        "requests" => Some(EventTopic::Requests),
+        "tasks" => Some(EventTopic::Tasks),
         "all" | "*" => Some(EventTopic::All),
         _ => None,
     }
@@ -623,6 +629,8 @@ mod tests {
         assert_eq!(parse_topic("budget"), Some(EventTopic::Budget));
         assert_eq!(parse_topic("rate_limit"), Some(EventTopic::RateLimit));
         assert_eq!(parse_topic("ratelimit"), Some(EventTopic::RateLimit));
+        assert_eq!(parse_topic("requests"), Some(EventTopic::Requests));
+        assert_eq!(parse_topic("tasks"), Some(EventTopic::Tasks));
         assert_eq!(parse_topic("all"), Some(EventTopic::All));
         assert_eq!(parse_topic("*"), Some(EventTopic::All));
         assert_eq!(parse_topic("invalid"), None);
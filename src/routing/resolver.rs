@@ -15,6 +15,7 @@ use crate::{
     db::DbPool,
     models::{DynamicProvider, ProviderOwner},
     observability::metrics,
+    providers::ProviderSettingsRegistry,
     secrets::SecretManager,
 };
 
@@ -605,11 +606,16 @@ pub async fn dynamic_provider_to_config(
                 models: std::collections::HashMap::new(),
                 retry: Default::default(),
                 circuit_breaker: Default::default(),
+                fair_queue: None,
+                adaptive_concurrency: None,
                 fallback_providers: Vec::new(),
                 model_fallbacks: std::collections::HashMap::new(),
                 health_check: Default::default(),
                 catalog_provider: None,
                 sovereignty: provider.sovereignty.clone(),
+                max_response_bytes: None,
+                endpoints: Vec::new(),
+                endpoint_selection: Default::default(),
             },
         )),
         "anthropic" => Ok(ProviderConfig::Anthropic(
@@ -628,17 +634,21 @@ pub async fn dynamic_provider_to_config(
                 models: std::collections::HashMap::new(),
                 retry: Default::default(),
                 circuit_breaker: Default::default(),
+                fair_queue: None,
+                adaptive_concurrency: None,
                 streaming_buffer: Default::default(),
                 fallback_providers: Vec::new(),
                 model_fallbacks: std::collections::HashMap::new(),
                 health_check: Default::default(),
                 catalog_provider: None,
                 sovereignty: provider.sovereignty.clone(),
+                max_response_bytes: None,
                 interleaved_thinking_models: crate::config::default_interleaved_thinking_models(),
                 adaptive_thinking_models: crate::config::default_adaptive_thinking_models(),
                 strict_thinking_models: crate::config::default_strict_thinking_models(),
                 mid_conversation_system_models:
                     crate::config::default_mid_conversation_system_models(),
+                prompt_caching: Default::default(),
             },
         )),
         #[cfg(feature = "provider-azure")]
@@ -666,11 +676,16 @@ pub async fn dynamic_provider_to_config(
                     models: std::collections::HashMap::new(),
                     retry: Default::default(),
                     circuit_breaker: Default::default(),
+                    fair_queue: None,
+                    adaptive_concurrency: None,
                     fallback_providers: Vec::new(),
                     model_fallbacks: std::collections::HashMap::new(),
                     health_check: Default::default(),
                     catalog_provider: None,
                     sovereignty: provider.sovereignty.clone(),
+                    max_response_bytes: None,
+                    endpoints: Vec::new(),
+                    endpoint_selection: Default::default(),
                 },
             ))
         }
@@ -726,6 +741,8 @@ pub async fn dynamic_provider_to_config(
                     models: std::collections::HashMap::new(),
                     retry: Default::default(),
                     circuit_breaker: Default::default(),
+                    fair_queue: None,
+                    adaptive_concurrency: None,
                     streaming_buffer: Default::default(),
                     fallback_providers: Vec::new(),
                     model_fallbacks: std::collections::HashMap::new(),
@@ -733,8 +750,10 @@ pub async fn dynamic_provider_to_config(
                     health_check: Default::default(),
                     catalog_provider: None,
                     sovereignty: provider.sovereignty.clone(),
+                    max_response_bytes: None,
                     interleaved_thinking_models: crate::config::default_interleaved_thinking_models(
                     ),
+                    prompt_caching: Default::default(),
                 },
             ))
         }
@@ -755,18 +774,24 @@ pub async fn dynamic_provider_to_config(
                         publisher,
                         base_url,
                         credentials: crate::config::GcpCredentials::Default,
+                        impersonate_service_accounts: Vec::new(),
+                        endpoints: Vec::new(),
+                        endpoint_selection: Default::default(),
                         timeout_secs: 60,
                         allowed_models: provider.models.clone(),
                         model_aliases: std::collections::HashMap::new(),
                         models: std::collections::HashMap::new(),
                         retry: Default::default(),
                         circuit_breaker: Default::default(),
+                        fair_queue: None,
+                        adaptive_concurrency: None,
                         streaming_buffer: Default::default(),
                         fallback_providers: Vec::new(),
                         model_fallbacks: std::collections::HashMap::new(),
                         health_check: Default::default(),
                         catalog_provider: None,
                         sovereignty: provider.sovereignty.clone(),
+                        max_response_bytes: None,
                     },
                 ))
             } else {
@@ -808,18 +833,24 @@ pub async fn dynamic_provider_to_config(
                         publisher,
                         base_url,
                         credentials,
+                        impersonate_service_accounts: Vec::new(),
+                        endpoints: Vec::new(),
+                        endpoint_selection: Default::default(),
                         timeout_secs: 60,
                         allowed_models: provider.models.clone(),
                         model_aliases: std::collections::HashMap::new(),
                         models: std::collections::HashMap::new(),
                         retry: Default::default(),
                         circuit_breaker: Default::default(),
+                        fair_queue: None,
+                        adaptive_concurrency: None,
                         streaming_buffer: Default::default(),
                         fallback_providers: Vec::new(),
                         model_fallbacks: std::collections::HashMap::new(),
                         health_check: Default::default(),
                         catalog_provider: None,
                         sovereignty: provider.sovereignty.clone(),
+                        max_response_bytes: None,
                     },
                 ))
             }
@@ -833,11 +864,14 @@ pub async fn dynamic_provider_to_config(
             models: std::collections::HashMap::new(),
             retry: Default::default(),
             circuit_breaker: Default::default(),
+            fair_queue: None,
+            adaptive_concurrency: None,
             fallback_providers: Vec::new(),
             model_fallbacks: std::collections::HashMap::new(),
             health_check: Default::default(),
             catalog_provider: None,
             sovereignty: provider.sovereignty.clone(),
+            max_response_bytes: None,
         })),
         _ => Err(RoutingError::InvalidScope(format!(
             "Unsupported provider type: {}",
@@ -860,22 +894,34 @@ pub struct ResolvedProviderInfo {
 /// This is a convenience function that handles both static and dynamic routes,
 /// returning the same format for easy use in API handlers.
 ///
-/// For static routes, it directly extracts the information.
+/// For static routes, it applies the provider's runtime settings to its file
+/// config, failing if the provider has been disabled.
 /// For dynamic routes, it performs database lookup with caching and secret resolution.
 pub async fn resolve_to_provider(
     routed: RoutedProvider<'_>,
     db: Option<&Arc<DbPool>>,
     cache: Option<&Arc<dyn Cache>>,
     secrets: Option<&Arc<dyn SecretManager>>,
+    provider_settings: &ProviderSettingsRegistry,
     auth: Option<&AuthenticatedRequest>,
 ) -> Result<ResolvedProviderInfo, RoutingError> {
     match routed {
-        RoutedProvider::Static(static_route) => Ok(ResolvedProviderInfo {
-            provider_name: static_route.provider_name.to_string(),
-            provider_config: static_route.provider_config.clone(),
-            model: static_route.model.to_string(),
-            source: "static",
-        }),
+        RoutedProvider::Static(static_route) => {
+            let provider_config = provider_settings
+                .apply(static_route.provider_name, static_route.provider_config)
+                .ok_or_else(|| {
+                    RoutingError::ProviderNotFound(format!(
+                        "Provider '{}' is disabled",
+                        static_route.provider_name
+                    ))
+                })?;
+            Ok(ResolvedProviderInfo {
+                provider_name: static_route.provider_name.to_string(),
+                provider_config,
+                model: static_route.model.to_string(),
+                source: "static",
+            })
+        }
         RoutedProvider::Dynamic(dynamic_route) => {
             // Resolve dynamic provider from database (with caching and secret resolution)
             let db = db.ok_or_else(|| {
@@ -0,0 +1,518 @@
+//! Go SDK: a single `hadrian` package with model structs and per-subsystem
+//! API types hanging off `Client`.
+
+use std::{collections::HashSet, fmt::Write};
+
+use serde_json::Value;
+
+use super::{
+    ApiModel, GeneratedFile, Operation, PathSegment, RequestBody, ResponseBody, Subsystem,
+    camel_case, features_list, first_line, is_nullable, non_null, object_properties, pascal_case,
+    path_segments, ref_name, render_template, snake_case,
+};
+
+const CLIENT_TEMPLATE: &str = include_str!("templates/go/client.go");
+const GO_MOD_TEMPLATE: &str = include_str!("templates/go/go.mod");
+
+const KEYWORDS: &[&str] = &[
+    "break",
+    "case",
+    "chan",
+    "const",
+    "continue",
+    "default",
+    "defer",
+    "else",
+    "fallthrough",
+    "for",
+    "func",
+    "go",
+    "goto",
+    "if",
+    "import",
+    "interface",
+    "map",
+    "package",
+    "range",
+    "return",
+    "select",
+    "struct",
+    "switch",
+    "type",
+    "var",
+];
+
+/// Locals and imports used inside generated method bodies.
+const RESERVED_LOCALS: &[&str] = &[
+    "a",
+    "body",
+    "contentType",
+    "ctx",
+    "err",
+    "fmt",
+    "out",
+    "params",
+    "payload",
+    "query",
+    "url",
+];
+
+/// Words Go style writes in all caps.
+const INITIALISMS: &[&str] = &[
+    "api", "cpu", "css", "dns", "html", "http", "https", "id", "ip", "json", "jwt", "mcp", "scim",
+    "sql", "sso", "tls", "ttl", "ui", "uri", "url", "uuid", "xml",
+];
+
+pub(super) fn render(model: &ApiModel) -> Vec<GeneratedFile> {
+    let features = features_list(model);
+    let type_names: HashSet<String> = model.schemas.keys().map(|n| pascal_case(n)).collect();
+
+    let mut fields = String::new();
+    let mut init = String::new();
+    let mut operations = String::new();
+    let mut imports = Imports::default();
+    for subsystem in Subsystem::ALL {
+        let ops: Vec<_> = model.operations_in(subsystem).collect();
+        if ops.is_empty() {
+            continue;
+        }
+        let api_type = format!("{}API", subsystem.type_prefix());
+        let field = go_ident(subsystem.as_str());
+        let _ = writeln!(fields, "\t{field} *{api_type}");
+        let _ = writeln!(init, "\tc.{field} = &{api_type}{{client: c}}");
+
+        let _ = write!(
+            operations,
+            "\n// {api_type} groups the {} endpoints.\ntype {api_type} struct {{\n\tclient *Client\n}}\n",
+            subsystem.as_str()
+        );
+        for op in ops {
+            operations.push('\n');
+            render_operation(&mut operations, &mut imports, &api_type, op, &type_names);
+        }
+    }
+
+    let client = render_template(
+        CLIENT_TEMPLATE,
+        &[
+            ("version", &model.version),
+            ("features", &features),
+            ("client_fields", fields.trim_end()),
+            ("client_init", init.trim_end()),
+        ],
+    );
+
+    let mut operations_go = format!(
+        "// Generated by `hadrian openapi --generate go` from Hadrian {}. Do not edit by hand.\n\npackage hadrian\n",
+        model.version
+    );
+    operations_go.push_str(&imports.render());
+    operations_go.push_str(&operations);
+
+    vec![
+        GeneratedFile {
+            path: "go.mod".into(),
+            contents: render_template(GO_MOD_TEMPLATE, &[("version", &model.version)]),
+        },
+        GeneratedFile {
+            path: "client.go".into(),
+            contents: client,
+        },
+        GeneratedFile {
+            path: "models.go".into(),
+            contents: render_models(model, &type_names),
+        },
+        GeneratedFile {
+            path: "operations.go".into(),
+            contents: operations_go,
+        },
+    ]
+}
+
+#[derive(Default)]
+struct Imports {
+    context: bool,
+    fmt: bool,
+    io: bool,
+    url: bool,
+}
+
+impl Imports {
+    fn render(&self) -> String {
+        let used = [
+            (self.context, "context"),
+            (self.fmt, "fmt"),
+            (self.io, "io"),
+            (self.url, "net/url"),
+        ];
+        let lines: Vec<String> = used
+            .iter()
+            .filter(|(used, _)| *used)
+            .map(|(_, pkg)| format!("\t\"{pkg}\"\n"))
+            .collect();
+        if lines.is_empty() {
+            String::new()
+        } else {
+            format!("\nimport (\n{})\n", lines.concat())
+        }
+    }
+}
+
+fn render_models(model: &ApiModel, type_names: &HashSet<String>) -> String {
+    let mut body = String::new();
+    let mut consts: HashSet<String> = HashSet::new();
+    for (name, schema) in &model.schemas {
+        let name = pascal_case(name);
+        body.push('\n');
+        write_doc(&mut body, &name, schema["description"].as_str());
+
+        if is_struct(schema) {
+            let _ = writeln!(body, "type {name} struct {{");
+            let mut seen = HashSet::new();
+            for (prop, prop_schema, required) in object_properties(schema) {
+                let mut field = go_ident(prop);
+                while !seen.insert(field.clone()) {
+                    field.push('_');
+                }
+                let ty = field_type(prop_schema, required);
+                let tag = if required {
+                    prop.to_string()
+                } else {
+                    format!("{prop},omitempty")
+                };
+                let _ = writeln!(body, "\t{field} {ty} `json:\"{tag}\"`");
+            }
+            body.push_str("}\n");
+            continue;
+        }
+
+        if let Some(values) = string_enum(schema) {
+            let _ = writeln!(body, "type {name} string\n\nconst (");
+            for value in values {
+                let mut constant = format!("{name}{}", go_ident(value));
+                while type_names.contains(&constant) || !consts.insert(constant.clone()) {
+                    constant.push_str("Value");
+                }
+                let _ = writeln!(body, "\t{constant} {name} = {}", go_string(value));
+            }
+            body.push_str(")\n");
+            continue;
+        }
+
+        let ty = go_type(schema);
+        if ty == "any" || ty.contains("json.RawMessage") {
+            let _ = writeln!(body, "type {name} = {ty}");
+        } else {
+            let _ = writeln!(body, "type {name} {ty}");
+        }
+    }
+
+    let mut out = format!(
+        "// Generated by `hadrian openapi --generate go` from Hadrian {}. Do not edit by hand.\n\npackage hadrian\n",
+        model.version
+    );
+    if body.contains("json.RawMessage") {
+        out.push_str("\nimport \"encoding/json\"\n");
+    }
+    out.push_str(&body);
+    out
+}
+
+fn field_type(schema: &Value, required: bool) -> String {
+    let ty = go_type(&non_null(schema));
+    if (required && !is_nullable(schema)) || !needs_pointer(&ty) {
+        ty
+    } else {
+        format!("*{ty}")
+    }
+}
+
+/// Slices, maps and interface types already have a zero value that means
+/// "absent", so they are never wrapped in a pointer.
+fn needs_pointer(ty: &str) -> bool {
+    !(ty.starts_with("[]") || ty.starts_with("map[") || ty == "any" || ty == "json.RawMessage")
+}
+
+fn render_operation(
+    out: &mut String,
+    imports: &mut Imports,
+    api_type: &str,
+    op: &Operation,
+    type_names: &HashSet<String>,
+) {
+    imports.context = true;
+    let method = pascal_case(&op.id);
+    let mut args = vec!["ctx context.Context".to_string()];
+
+    let mut path_expr = Vec::new();
+    for segment in path_segments(&op.path) {
+        match segment {
+            PathSegment::Literal(lit) => path_expr.push(go_string(lit)),
+            PathSegment::Param(name) => {
+                imports.url = true;
+                let ident = local_name(name);
+                let ty = op
+                    .path_params
+                    .iter()
+                    .find(|p| p.name == name)
+                    .map(|p| go_type(&non_null(&p.schema)))
+                    .unwrap_or_else(|| "string".into());
+                if ty == "string" {
+                    path_expr.push(format!("url.PathEscape({ident})"));
+                } else {
+                    imports.fmt = true;
+                    path_expr.push(format!("url.PathEscape(fmt.Sprint({ident}))"));
+                }
+                args.push(format!("{ident} {ty}"));
+            }
+        }
+    }
+
+    let mut prelude = String::new();
+    let body_arg = match &op.body {
+        Some(RequestBody::Json { schema, required }) => {
+            let ty = go_type(&non_null(schema));
+            if *required || !needs_pointer(&ty) {
+                args.push(format!("body {ty}"));
+                "body"
+            } else {
+                args.push(format!("body *{ty}"));
+                // A typed nil pointer would otherwise be sent as `null`
+                prelude
+                    .push_str("\tvar payload any\n\tif body != nil {\n\t\tpayload = body\n\t}\n");
+                "payload"
+            }
+        }
+        Some(RequestBody::Multipart) => {
+            imports.io = true;
+            args.push("body io.Reader".into());
+            args.push("contentType string".into());
+            "multipartBody{reader: body, contentType: contentType}"
+        }
+        None => "nil",
+    };
+
+    let mut params_type = String::new();
+    let query_arg = if op.query_params.is_empty() {
+        "nil"
+    } else {
+        imports.url = true;
+        imports.fmt = true;
+        let mut name = format!("{method}Params");
+        while type_names.contains(&name) {
+            name.push_str("Query");
+        }
+        let _ = writeln!(
+            params_type,
+            "// {name} holds the query parameters for {method}.\ntype {name} struct {{"
+        );
+        prelude.push_str("\tquery := url.Values{}\n\tif params != nil {\n");
+        let mut seen = HashSet::new();
+        for param in &op.query_params {
+            let mut field = go_ident(&param.name);
+            while !seen.insert(field.clone()) {
+                field.push('_');
+            }
+            let ty = field_type(&param.schema, param.required);
+            if let Some(doc) = param.description.as_deref().map(first_line)
+                && !doc.is_empty()
+            {
+                let _ = writeln!(params_type, "\t// {doc}");
+            }
+            let _ = writeln!(params_type, "\t{field} {ty}");
+
+            let key = go_string(&param.name);
+            if ty.starts_with("[]") {
+                let _ = writeln!(
+                    prelude,
+                    "\t\tfor _, v := range params.{field} {{\n\t\t\tquery.Add({key}, fmt.Sprint(v))\n\t\t}}"
+                );
+            } else if ty.starts_with('*') {
+                let _ = writeln!(
+                    prelude,
+                    "\t\tif params.{field} != nil {{\n\t\t\tquery.Set({key}, fmt.Sprint(*params.{field}))\n\t\t}}"
+                );
+            } else {
+                let _ = writeln!(prelude, "\t\tquery.Set({key}, fmt.Sprint(params.{field}))");
+            }
+        }
+        params_type.push_str("}\n\n");
+        prelude.push_str("\t}\n");
+        args.push(format!("params *{name}"));
+        "query"
+    };
+
+    let path_expr = if path_expr.is_empty() {
+        "\"/\"".to_string()
+    } else {
+        path_expr.join(" + ")
+    };
+    let call = format!(
+        "a.client.do(ctx, {}, {path_expr}, {query_arg}, {body_arg}",
+        go_string(&op.method)
+    );
+    let (ret, tail) = match &op.response {
+        ResponseBody::Json(schema) => {
+            let ty = go_type(&non_null(schema));
+            (
+                format!("({ty}, error)"),
+                format!("\tvar out {ty}\n\terr := {call}, &out)\n\treturn out, err\n"),
+            )
+        }
+        ResponseBody::Binary => (
+            "([]byte, error)".to_string(),
+            format!("\tvar out []byte\n\terr := {call}, &out)\n\treturn out, err\n"),
+        ),
+        ResponseBody::Empty => ("error".to_string(), format!("\treturn {call}, nil)\n")),
+    };
+
+    out.push_str(&params_type);
+    let _ = writeln!(out, "// {method} calls {} {}.", op.method, op.path);
+    if let Some(summary) = op.summary.as_deref().map(first_line)
+        && !summary.is_empty()
+    {
+        let _ = writeln!(out, "//\n// {summary}");
+    }
+    let _ = writeln!(
+        out,
+        "func (a *{api_type}) {method}({}) {ret} {{",
+        args.join(", ")
+    );
+    out.push_str(&prelude);
+    out.push_str(&tail);
+    out.push_str("}\n");
+}
+
+/// Map a JSON schema to a Go type. Unions Go can't express become
+/// `json.RawMessage` so callers can decode them themselves.
+pub(super) fn go_type(schema: &Value) -> String {
+    if let Some(name) = schema["$ref"].as_str().and_then(ref_name) {
+        return pascal_case(name);
+    }
+    if schema.get("const").is_some() || schema.get("enum").is_some() {
+        return match &schema["type"] {
+            Value::String(t) if t != "string" => scalar(t).unwrap_or("any").to_string(),
+            _ => "string".into(),
+        };
+    }
+    for key in ["oneOf", "anyOf"] {
+        if let Some(variants) = schema[key].as_array() {
+            let rest: Vec<&Value> = variants.iter().filter(|v| v["type"] != "null").collect();
+            return match rest.as_slice() {
+                [only] => go_type(only),
+                _ => "json.RawMessage".into(),
+            };
+        }
+    }
+    if let Some(parts) = schema["allOf"].as_array() {
+        return match parts.as_slice() {
+            [only] => go_type(only),
+            _ => "json.RawMessage".into(),
+        };
+    }
+    match &schema["type"] {
+        Value::Array(types) => {
+            let rest: Vec<&Value> = types.iter().filter(|t| *t != "null").collect();
+            match rest.as_slice() {
+                [only] => {
+                    let mut single = schema.clone();
+                    single["type"] = (*only).clone();
+                    go_type(&single)
+                }
+                _ => "json.RawMessage".into(),
+            }
+        }
+        Value::String(t) if t == "array" => format!("[]{}", go_type(&schema["items"])),
+        Value::String(t) if t == "object" => match schema.get("additionalProperties") {
+            Some(additional) if additional.is_object() => {
+                format!("map[string]{}", go_type(additional))
+            }
+            _ => "map[string]any".into(),
+        },
+        Value::String(t) => scalar(t).unwrap_or("any").to_string(),
+        _ if schema.get("properties").is_some() => "map[string]any".into(),
+        _ => "any".into(),
+    }
+}
+
+fn scalar(ty: &str) -> Option<&'static str> {
+    match ty {
+        "string" => Some("string"),
+        "integer" => Some("int64"),
+        "number" => Some("float64"),
+        "boolean" => Some("bool"),
+        _ => None,
+    }
+}
+
+fn is_struct(schema: &Value) -> bool {
+    schema.get("oneOf").is_none()
+        && schema.get("anyOf").is_none()
+        && schema.get("allOf").is_none()
+        && schema["properties"]
+            .as_object()
+            .is_some_and(|p| !p.is_empty())
+}
+
+fn string_enum(schema: &Value) -> Option<Vec<&str>> {
+    if schema["type"] != "string" {
+        return None;
+    }
+    schema["enum"]
+        .as_array()?
+        .iter()
+        .map(Value::as_str)
+        .collect()
+}
+
+/// Exported Go identifier, with common initialisms upper-cased (`team_id` ->
+/// `TeamID`).
+fn go_ident(name: &str) -> String {
+    let ident: String = snake_case(name)
+        .split('_')
+        .map(|word| {
+            if INITIALISMS.contains(&word) {
+                word.to_ascii_uppercase()
+            } else {
+                let mut chars = word.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                    None => String::new(),
+                }
+            }
+        })
+        .collect();
+    if ident.is_empty() {
+        "Value".into()
+    } else if ident.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("V{ident}")
+    } else {
+        ident
+    }
+}
+
+fn local_name(name: &str) -> String {
+    let ident = camel_case(name);
+    if KEYWORDS.contains(&ident.as_str()) || RESERVED_LOCALS.contains(&ident.as_str()) {
+        format!("{ident}Param")
+    } else {
+        ident
+    }
+}
+
+fn go_string(s: &str) -> String {
+    Value::String(s.to_string()).to_string()
+}
+
+fn write_doc(out: &mut String, name: &str, description: Option<&str>) {
+    let Some(description) = description.map(str::trim).filter(|d| !d.is_empty()) else {
+        return;
+    };
+    let mut lines = description.lines();
+    if let Some(first) = lines.next() {
+        let _ = writeln!(out, "// {name}: {first}");
+    }
+    for line in lines {
+        let _ = writeln!(out, "// {line}");
+    }
+}
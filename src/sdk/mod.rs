@@ -0,0 +1,836 @@
+//! Typed client SDK generation.
+//!
+//! `hadrian openapi --generate <language>` feeds the spec built by
+//! [`crate::openapi::ApiDoc`] through [`prepare_spec`] and renders it with the
+//! templates bundled under `src/sdk/templates/`. Because the spec is produced
+//! by the running binary, the generated client covers exactly the endpoints
+//! compiled into it: routes behind disabled features are absent, and schemas
+//! only those routes used are pruned.
+//!
+//! Operations are grouped by subsystem ([`Subsystem`]) so each client exposes
+//! `api`, `admin` and `system` namespaces rather than one flat list of
+//! several hundred methods.
+
+mod go;
+mod python;
+mod typescript;
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    fmt,
+    str::FromStr,
+};
+
+use serde_json::{Map, Value, json};
+
+/// Target language for a generated SDK.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SdkLanguage {
+    TypeScript,
+    Python,
+    Go,
+}
+
+impl SdkLanguage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::TypeScript => "typescript",
+            Self::Python => "python",
+            Self::Go => "go",
+        }
+    }
+}
+
+impl fmt::Display for SdkLanguage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SdkLanguage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "typescript" | "ts" => Ok(Self::TypeScript),
+            "python" | "py" => Ok(Self::Python),
+            "go" | "golang" => Ok(Self::Go),
+            other => Err(format!(
+                "unknown SDK language '{other}' (expected typescript, python or go)"
+            )),
+        }
+    }
+}
+
+/// Part of the gateway an operation belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Subsystem {
+    /// OpenAI-compatible data plane (`/v1/...`, `/api/v1/...`)
+    Api,
+    /// Admin API (`/admin/v1/...`)
+    Admin,
+    /// Health checks, browser auth, OAuth, SCIM and federation endpoints
+    System,
+}
+
+impl Subsystem {
+    pub const ALL: [Subsystem; 3] = [Self::Api, Self::Admin, Self::System];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Api => "api",
+            Self::Admin => "admin",
+            Self::System => "system",
+        }
+    }
+
+    /// Prefix for the per-subsystem client class (`PublicApi`, `AdminApi`, ...)
+    pub fn type_prefix(&self) -> &'static str {
+        match self {
+            Self::Api => "Public",
+            Self::Admin => "Admin",
+            Self::System => "System",
+        }
+    }
+
+    fn for_path(path: &str) -> Self {
+        if path.starts_with("/admin/") {
+            Self::Admin
+        } else if path.starts_with("/v1/") || path.starts_with("/api/v1/") {
+            Self::Api
+        } else {
+            Self::System
+        }
+    }
+}
+
+/// A file produced by a generator, relative to the output directory.
+#[derive(Debug, Clone)]
+pub struct GeneratedFile {
+    pub path: String,
+    pub contents: String,
+}
+
+/// A path or query parameter.
+#[derive(Debug, Clone)]
+pub struct Param {
+    pub name: String,
+    pub required: bool,
+    pub description: Option<String>,
+    pub schema: Value,
+}
+
+/// How an operation's request body is sent.
+#[derive(Debug, Clone)]
+pub enum RequestBody {
+    Json {
+        schema: Value,
+        required: bool,
+    },
+    /// `multipart/form-data` uploads; passed through as-is by the clients.
+    Multipart,
+}
+
+/// What an operation's success response contains.
+#[derive(Debug, Clone)]
+pub enum ResponseBody {
+    Json(Value),
+    /// Non-JSON or undocumented content (files, CSV, audio, event streams)
+    Binary,
+    Empty,
+}
+
+/// One HTTP operation, flattened out of the spec for the generators.
+#[derive(Debug, Clone)]
+pub struct Operation {
+    /// `operationId`, always `snake_case`
+    pub id: String,
+    pub subsystem: Subsystem,
+    /// Uppercase HTTP method
+    pub method: String,
+    pub path: String,
+    pub summary: Option<String>,
+    pub path_params: Vec<Param>,
+    pub query_params: Vec<Param>,
+    pub body: Option<RequestBody>,
+    pub response: ResponseBody,
+}
+
+/// Everything a generator needs to render a client.
+#[derive(Debug, Clone)]
+pub struct ApiModel {
+    pub version: String,
+    pub features: Vec<String>,
+    pub schemas: BTreeMap<String, Value>,
+    pub operations: Vec<Operation>,
+}
+
+impl ApiModel {
+    /// Extract operations and schemas from a spec that has been through
+    /// [`prepare_spec`].
+    pub fn from_spec(spec: &Value) -> Self {
+        let version = spec["info"]["version"]
+            .as_str()
+            .unwrap_or("0.0.0")
+            .to_string();
+        let features = spec["info"]["x-hadrian-features"]
+            .as_array()
+            .map(|f| {
+                f.iter()
+                    .filter_map(|v| v.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let schemas = spec["components"]["schemas"]
+            .as_object()
+            .map(|s| s.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+            .unwrap_or_default();
+
+        let mut operations = Vec::new();
+        for (path, method, op) in operations_of(spec) {
+            let mut path_params = Vec::new();
+            let mut query_params = Vec::new();
+            for param in op["parameters"].as_array().into_iter().flatten() {
+                let parsed = Param {
+                    name: param["name"].as_str().unwrap_or_default().to_string(),
+                    required: param["required"].as_bool().unwrap_or(false),
+                    description: param["description"].as_str().map(str::to_string),
+                    schema: param.get("schema").cloned().unwrap_or(json!({})),
+                };
+                match param["in"].as_str() {
+                    Some("path") => path_params.push(parsed),
+                    Some("query") => query_params.push(parsed),
+                    // Header and cookie parameters are set by the client runtime
+                    _ => {}
+                }
+            }
+
+            let body = op.get("requestBody").map(|rb| {
+                let content = &rb["content"];
+                match content.get("application/json") {
+                    Some(json_body) => RequestBody::Json {
+                        schema: json_body.get("schema").cloned().unwrap_or(json!({})),
+                        required: rb["required"].as_bool().unwrap_or(false),
+                    },
+                    None => RequestBody::Multipart,
+                }
+            });
+
+            operations.push(Operation {
+                id: op["operationId"].as_str().unwrap_or_default().to_string(),
+                subsystem: Subsystem::for_path(path),
+                method: method.to_ascii_uppercase(),
+                path: path.to_string(),
+                summary: op["summary"].as_str().map(str::to_string),
+                path_params,
+                query_params,
+                body,
+                response: success_response(op),
+            });
+        }
+
+        Self {
+            version,
+            features,
+            schemas,
+            operations,
+        }
+    }
+
+    /// Operations belonging to one subsystem, in spec order.
+    pub fn operations_in(&self, subsystem: Subsystem) -> impl Iterator<Item = &Operation> {
+        self.operations
+            .iter()
+            .filter(move |op| op.subsystem == subsystem)
+    }
+}
+
+/// Post-process the OpenAPI spec for SDK generation.
+///
+/// - Records the compiled feature set in `info.x-hadrian-features`.
+/// - Tags every operation with `x-hadrian-subsystem`.
+/// - Normalizes `operationId`s to unique `snake_case` names, deriving one from
+///   the method and path when missing.
+/// - Drops component schemas that no remaining operation references.
+pub fn prepare_spec(spec: &mut Value, features: &[&str]) {
+    if let Some(info) = spec.get_mut("info").and_then(Value::as_object_mut) {
+        info.insert("x-hadrian-features".into(), json!(features));
+    }
+
+    let mut seen = HashSet::new();
+    if let Some(paths) = spec.get_mut("paths").and_then(Value::as_object_mut) {
+        for (path, item) in paths.iter_mut() {
+            let Some(item) = item.as_object_mut() else {
+                continue;
+            };
+            for (method, op) in item.iter_mut() {
+                let Some(op) = op.as_object_mut().filter(|_| is_http_method(method)) else {
+                    continue;
+                };
+                op.insert(
+                    "x-hadrian-subsystem".into(),
+                    json!(Subsystem::for_path(path).as_str()),
+                );
+
+                let base = op
+                    .get("operationId")
+                    .and_then(Value::as_str)
+                    .map(snake_case)
+                    .filter(|id| !id.is_empty())
+                    .unwrap_or_else(|| snake_case(&format!("{method} {path}")));
+                let mut id = base.clone();
+                let mut n = 2;
+                while !seen.insert(id.clone()) {
+                    id = format!("{base}_{n}");
+                    n += 1;
+                }
+                op.insert("operationId".into(), json!(id));
+            }
+        }
+    }
+
+    prune_unreferenced_schemas(spec);
+}
+
+/// Render an SDK for `language` from a prepared spec.
+pub fn generate(language: SdkLanguage, spec: &Value) -> Vec<GeneratedFile> {
+    let model = ApiModel::from_spec(spec);
+    match language {
+        SdkLanguage::TypeScript => typescript::render(&model),
+        SdkLanguage::Python => python::render(&model),
+        SdkLanguage::Go => go::render(&model),
+    }
+}
+
+fn is_http_method(method: &str) -> bool {
+    matches!(
+        method,
+        "get" | "put" | "post" | "delete" | "options" | "head" | "patch" | "trace"
+    )
+}
+
+/// Iterate `(path, method, operation)` over a spec in document order.
+fn operations_of(spec: &Value) -> impl Iterator<Item = (&str, &str, &Value)> {
+    spec["paths"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .flat_map(|(path, item)| {
+            item.as_object()
+                .into_iter()
+                .flatten()
+                .filter(|(method, _)| is_http_method(method))
+                .map(move |(method, op)| (path.as_str(), method.as_str(), op))
+        })
+}
+
+fn success_response(op: &Value) -> ResponseBody {
+    let Some(responses) = op["responses"].as_object() else {
+        return ResponseBody::Empty;
+    };
+    let Some((code, response)) = responses.iter().find(|(code, _)| code.starts_with('2')) else {
+        return ResponseBody::Empty;
+    };
+    let Some(content) = response["content"].as_object().filter(|c| !c.is_empty()) else {
+        // Undocumented bodies (mostly SSE streams) are handed back raw
+        return if code == "204" {
+            ResponseBody::Empty
+        } else {
+            ResponseBody::Binary
+        };
+    };
+    match content.get("application/json") {
+        Some(body) => ResponseBody::Json(body.get("schema").cloned().unwrap_or(json!({}))),
+        None => ResponseBody::Binary,
+    }
+}
+
+/// Remove `components.schemas` entries unreachable from any path.
+fn prune_unreferenced_schemas(spec: &mut Value) {
+    let Some(schemas) = spec["components"]["schemas"].as_object().cloned() else {
+        return;
+    };
+
+    let mut reachable = BTreeSet::new();
+    let mut pending = Vec::new();
+    collect_refs(&spec["paths"], &mut pending);
+    while let Some(name) = pending.pop() {
+        if reachable.insert(name.clone())
+            && let Some(schema) = schemas.get(&name)
+        {
+            collect_refs(schema, &mut pending);
+        }
+    }
+
+    let pruned: Map<String, Value> = schemas
+        .into_iter()
+        .filter(|(name, _)| reachable.contains(name))
+        .collect();
+    spec["components"]["schemas"] = Value::Object(pruned);
+}
+
+fn collect_refs(value: &Value, out: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            if let Some(name) = map.get("$ref").and_then(Value::as_str).and_then(ref_name) {
+                out.push(name.to_string());
+            }
+            map.values().for_each(|v| collect_refs(v, out));
+        }
+        Value::Array(items) => items.iter().for_each(|v| collect_refs(v, out)),
+        _ => {}
+    }
+}
+
+/// Schema name from a `#/components/schemas/<name>` reference.
+pub(crate) fn ref_name(reference: &str) -> Option<&str> {
+    reference.strip_prefix("#/components/schemas/")
+}
+
+/// Split an identifier-ish string into lowercase words.
+fn words(s: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut prev: Option<char> = None;
+    for c in s.chars() {
+        if !c.is_ascii_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            prev = None;
+            continue;
+        }
+        let boundary = match prev {
+            Some(p) => c.is_ascii_uppercase() && (p.is_ascii_lowercase() || p.is_ascii_digit()),
+            None => false,
+        };
+        if boundary && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        current.push(c.to_ascii_lowercase());
+        prev = Some(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+pub(crate) fn snake_case(s: &str) -> String {
+    words(s).join("_")
+}
+
+pub(crate) fn pascal_case(s: &str) -> String {
+    let name: String = words(s)
+        .iter()
+        .map(|w| {
+            let mut chars = w.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("T{name}")
+    } else {
+        name
+    }
+}
+
+pub(crate) fn camel_case(s: &str) -> String {
+    let pascal = pascal_case(s);
+    let mut chars = pascal.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+/// Whether a schema allows `null` (OpenAPI 3.1 type lists or a `null` branch).
+pub(crate) fn is_nullable(schema: &Value) -> bool {
+    if let Some(types) = schema["type"].as_array() {
+        return types.iter().any(|t| t == "null");
+    }
+    schema["oneOf"]
+        .as_array()
+        .or_else(|| schema["anyOf"].as_array())
+        .is_some_and(|variants| variants.iter().any(|v| v["type"] == "null"))
+}
+
+/// The schema with any `null` alternative removed, so generators can map the
+/// remaining type and apply their own optional wrapper.
+pub(crate) fn non_null(schema: &Value) -> Value {
+    let mut schema = schema.clone();
+    if let Some(types) = schema["type"].as_array() {
+        let rest: Vec<Value> = types.iter().filter(|t| *t != "null").cloned().collect();
+        schema["type"] = match rest.len() {
+            1 => rest[0].clone(),
+            _ => Value::Array(rest),
+        };
+        return schema;
+    }
+    for key in ["oneOf", "anyOf"] {
+        if let Some(variants) = schema[key].as_array() {
+            let rest: Vec<Value> = variants
+                .iter()
+                .filter(|v| v["type"] != "null")
+                .cloned()
+                .collect();
+            if rest.len() == 1 {
+                let mut only = rest[0].clone();
+                if let (Some(only), Some(desc)) =
+                    (only.as_object_mut(), schema.get("description").cloned())
+                {
+                    only.entry("description").or_insert(desc);
+                }
+                return only;
+            }
+            schema[key] = Value::Array(rest);
+        }
+    }
+    schema
+}
+
+/// Properties of an object schema, with whether each is required.
+pub(crate) fn object_properties(schema: &Value) -> Vec<(&str, &Value, bool)> {
+    let required: HashSet<&str> = schema["required"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect();
+    schema["properties"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(name, prop)| (name.as_str(), prop, required.contains(name.as_str())))
+        .collect()
+}
+
+/// Substitute `{{key}}` placeholders in a bundled template.
+pub(crate) fn render_template(template: &str, vars: &[(&str, &str)]) -> String {
+    vars.iter().fold(template.to_string(), |acc, (key, value)| {
+        acc.replace(&format!("{{{{{key}}}}}"), value)
+    })
+}
+
+/// Comma-separated feature list for generated file headers.
+pub(crate) fn features_list(model: &ApiModel) -> String {
+    if model.features.is_empty() {
+        "none".to_string()
+    } else {
+        model.features.join(", ")
+    }
+}
+
+/// Split a path template into literal and `{param}` segments.
+pub(crate) fn path_segments(path: &str) -> Vec<PathSegment<'_>> {
+    let mut segments = Vec::new();
+    let mut rest = path;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        if start > 0 {
+            segments.push(PathSegment::Literal(&rest[..start]));
+        }
+        segments.push(PathSegment::Param(&rest[start + 1..start + len]));
+        rest = &rest[start + len + 1..];
+    }
+    if !rest.is_empty() {
+        segments.push(PathSegment::Literal(rest));
+    }
+    segments
+}
+
+pub(crate) enum PathSegment<'a> {
+    Literal(&'a str),
+    Param(&'a str),
+}
+
+/// Collapse a description into a single line for inline doc comments.
+pub(crate) fn first_line(description: &str) -> &str {
+    description.lines().next().unwrap_or_default().trim()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_spec() -> Value {
+        json!({
+            "openapi": "3.1.0",
+            "info": {"title": "Hadrian", "version": "1.2.3"},
+            "paths": {
+                "/admin/v1/organizations/{org_slug}/projects": {
+                    "get": {
+                        "operationId": "project_list",
+                        "summary": "List projects in an organization",
+                        "parameters": [
+                            {"name": "org_slug", "in": "path", "required": true, "schema": {"type": "string"}},
+                            {"name": "limit", "in": "query", "required": false, "schema": {"type": ["integer", "null"], "format": "int64"}}
+                        ],
+                        "responses": {
+                            "200": {"description": "ok", "content": {"application/json": {"schema": {"$ref": "#/components/schemas/ProjectList"}}}}
+                        }
+                    },
+                    "post": {
+                        "operationId": "projectCreate",
+                        "parameters": [
+                            {"name": "org_slug", "in": "path", "required": true, "schema": {"type": "string"}}
+                        ],
+                        "requestBody": {"required": true, "content": {"application/json": {"schema": {"$ref": "#/components/schemas/CreateProject"}}}},
+                        "responses": {
+                            "201": {"description": "created", "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Project"}}}}
+                        }
+                    }
+                },
+                "/v1/files": {
+                    "post": {
+                        "requestBody": {"content": {"multipart/form-data": {"schema": {"type": "object"}}}},
+                        "responses": {"200": {"description": "ok", "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Project"}}}}}
+                    }
+                },
+                "/health": {
+                    "get": {
+                        "operationId": "project_list",
+                        "responses": {"204": {"description": "ok"}}
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "ProjectList": {
+                        "type": "object",
+                        "required": ["data"],
+                        "properties": {"data": {"type": "array", "items": {"$ref": "#/components/schemas/Project"}}}
+                    },
+                    "Project": {
+                        "type": "object",
+                        "required": ["id", "status"],
+                        "properties": {
+                            "id": {"type": "string", "format": "uuid"},
+                            "team_id": {"type": ["string", "null"], "description": "Owning team"},
+                            "status": {"$ref": "#/components/schemas/ProjectStatus"}
+                        }
+                    },
+                    "ProjectStatus": {"type": "string", "enum": ["active", "archived"]},
+                    "CreateProject": {
+                        "type": "object",
+                        "required": ["slug"],
+                        "properties": {"slug": {"type": "string"}}
+                    },
+                    "SamlOnly": {"type": "object", "properties": {"x": {"type": "string"}}}
+                }
+            }
+        })
+    }
+
+    fn prepared() -> Value {
+        let mut spec = sample_spec();
+        prepare_spec(&mut spec, &["sso", "utoipa"]);
+        spec
+    }
+
+    #[test]
+    fn test_language_from_str() {
+        assert_eq!("TypeScript".parse(), Ok(SdkLanguage::TypeScript));
+        assert_eq!("py".parse(), Ok(SdkLanguage::Python));
+        assert_eq!("golang".parse(), Ok(SdkLanguage::Go));
+        assert!("rust".parse::<SdkLanguage>().is_err());
+    }
+
+    #[test]
+    fn test_prepare_spec() {
+        let spec = prepared();
+        assert_eq!(spec["info"]["x-hadrian-features"], json!(["sso", "utoipa"]));
+
+        let projects = &spec["paths"]["/admin/v1/organizations/{org_slug}/projects"];
+        assert_eq!(projects["get"]["x-hadrian-subsystem"], "admin");
+        assert_eq!(projects["post"]["operationId"], "project_create");
+        assert_eq!(
+            spec["paths"]["/v1/files"]["post"]["x-hadrian-subsystem"],
+            "api"
+        );
+        assert_eq!(
+            spec["paths"]["/v1/files"]["post"]["operationId"],
+            "post_v1_files"
+        );
+        // Duplicate IDs are disambiguated rather than silently shadowed
+        assert_eq!(
+            spec["paths"]["/health"]["get"]["operationId"],
+            "project_list_2"
+        );
+        assert_eq!(
+            spec["paths"]["/health"]["get"]["x-hadrian-subsystem"],
+            "system"
+        );
+
+        let schemas = spec["components"]["schemas"].as_object().unwrap();
+        assert!(
+            schemas.contains_key("ProjectStatus"),
+            "transitive refs kept"
+        );
+        assert!(
+            !schemas.contains_key("SamlOnly"),
+            "unreferenced schema pruned"
+        );
+    }
+
+    #[test]
+    fn test_api_model() {
+        let model = ApiModel::from_spec(&prepared());
+        assert_eq!(model.version, "1.2.3");
+        assert_eq!(model.operations.len(), 4);
+
+        let list = &model.operations[0];
+        assert_eq!(list.method, "GET");
+        assert_eq!(list.path_params.len(), 1);
+        assert_eq!(list.query_params[0].name, "limit");
+        assert!(matches!(list.response, ResponseBody::Json(_)));
+
+        let upload = model.operations_in(Subsystem::Api).next().unwrap();
+        assert!(matches!(upload.body, Some(RequestBody::Multipart)));
+        let health = model.operations_in(Subsystem::System).next().unwrap();
+        assert!(matches!(health.response, ResponseBody::Empty));
+    }
+
+    #[test]
+    fn test_case_conversion() {
+        assert_eq!(snake_case("projectCreate"), "project_create");
+        assert_eq!(snake_case("get /admin/v1/dlq/{id}"), "get_admin_v1_dlq_id");
+        assert_eq!(pascal_case("org_sso_config"), "OrgSsoConfig");
+        assert_eq!(pascal_case("ListResponse_Project"), "ListResponseProject");
+        assert_eq!(
+            camel_case("api_v1_chat_completions"),
+            "apiV1ChatCompletions"
+        );
+        assert_eq!(pascal_case("2fa"), "T2fa");
+    }
+
+    #[test]
+    fn test_nullable() {
+        let schema = json!({"type": ["string", "null"]});
+        assert!(is_nullable(&schema));
+        assert_eq!(non_null(&schema)["type"], "string");
+
+        let schema = json!({"oneOf": [{"type": "null"}, {"$ref": "#/components/schemas/A"}]});
+        assert!(is_nullable(&schema));
+        assert_eq!(non_null(&schema)["$ref"], "#/components/schemas/A");
+        assert!(!is_nullable(&json!({"type": "string"})));
+    }
+
+    #[test]
+    fn test_generate_all_languages() {
+        let spec = prepared();
+        for language in [
+            SdkLanguage::TypeScript,
+            SdkLanguage::Python,
+            SdkLanguage::Go,
+        ] {
+            let files = generate(language, &spec);
+            let all: String = files.iter().map(|f| f.contents.as_str()).collect();
+            assert!(!files.is_empty(), "{language}");
+            assert!(!all.contains("{{"), "{language}: unrendered placeholder");
+            assert!(all.contains("1.2.3"), "{language}: version missing");
+            assert!(
+                !all.contains("SamlOnly"),
+                "{language}: pruned schema rendered"
+            );
+        }
+    }
+
+    #[test]
+    fn test_typescript_output() {
+        let files = generate(SdkLanguage::TypeScript, &prepared());
+        let types = &files
+            .iter()
+            .find(|f| f.path == "types.ts")
+            .unwrap()
+            .contents;
+        assert!(types.contains("export interface Project {"));
+        assert!(types.contains("  team_id?: string | null;"));
+        assert!(types.contains(r#"export type ProjectStatus = "active" | "archived";"#));
+
+        let client = &files
+            .iter()
+            .find(|f| f.path == "client.ts")
+            .unwrap()
+            .contents;
+        assert!(
+            client
+                .contains("async projectList(orgSlug: string, query?: { limit?: number | null })")
+        );
+        assert!(
+            client.contains(
+                "`/admin/v1/organizations/${encodeURIComponent(String(orgSlug))}/projects`"
+            )
+        );
+        assert!(client.contains("async projectCreate(orgSlug: string, body: T.CreateProject)"));
+    }
+
+    #[test]
+    fn test_python_output() {
+        let files = generate(SdkLanguage::Python, &prepared());
+        let models = &files
+            .iter()
+            .find(|f| f.path == "hadrian_client/models.py")
+            .unwrap()
+            .contents;
+        assert!(models.contains("class Project(TypedDict, total=False):"));
+        assert!(models.contains("    id: Required[str]"));
+        assert!(models.contains(r#"ProjectStatus: TypeAlias = "Literal['active', 'archived']""#));
+
+        let client = &files
+            .iter()
+            .find(|f| f.path == "hadrian_client/client.py")
+            .unwrap()
+            .contents;
+        assert!(
+            client.contains("def project_list(self, org_slug: str, *, limit: int | None = None)")
+        );
+    }
+
+    #[test]
+    fn test_go_output() {
+        let files = generate(SdkLanguage::Go, &prepared());
+        let models = &files
+            .iter()
+            .find(|f| f.path == "models.go")
+            .unwrap()
+            .contents;
+        assert!(models.contains("type Project struct {"));
+        assert!(models.contains("\tTeamID *string `json:\"team_id,omitempty\"`"));
+        assert!(models.contains("type ProjectStatus string"));
+
+        let ops = &files
+            .iter()
+            .find(|f| f.path == "operations.go")
+            .unwrap()
+            .contents;
+        assert!(ops.contains(
+            "func (a *AdminAPI) ProjectList(ctx context.Context, orgSlug string, params *ProjectListParams) (ProjectList, error)"
+        ));
+    }
+
+    #[cfg(feature = "utoipa")]
+    #[test]
+    fn test_generate_from_gateway_spec() {
+        let mut spec = serde_json::to_value(crate::openapi::ApiDoc::build()).unwrap();
+        prepare_spec(&mut spec, &["utoipa"]);
+        let model = ApiModel::from_spec(&spec);
+        assert!(model.operations_in(Subsystem::Admin).count() > 0);
+        assert!(model.operations_in(Subsystem::Api).count() > 0);
+
+        for language in [
+            SdkLanguage::TypeScript,
+            SdkLanguage::Python,
+            SdkLanguage::Go,
+        ] {
+            let files = generate(language, &spec);
+            let all: String = files.iter().map(|f| f.contents.as_str()).collect();
+            assert!(!all.contains("{{"), "{language}: unrendered placeholder");
+        }
+    }
+}
@@ -0,0 +1,385 @@
+//! Python SDK: a `hadrian_client` package with `TypedDict` models and an
+//! httpx-based client.
+
+use std::fmt::Write;
+
+use serde_json::Value;
+
+use super::{
+    ApiModel, GeneratedFile, Operation, PathSegment, RequestBody, ResponseBody, Subsystem,
+    features_list, first_line, non_null, object_properties, pascal_case, path_segments, ref_name,
+    render_template, snake_case,
+};
+
+const CLIENT_TEMPLATE: &str = include_str!("templates/python/client.py");
+const PYPROJECT_TEMPLATE: &str = include_str!("templates/python/pyproject.toml");
+
+const KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+/// Names a generated method already uses for its own arguments or imports.
+const RESERVED_ARGS: &[&str] = &["self", "body", "files", "query", "models"];
+
+pub(super) fn render(model: &ApiModel) -> Vec<GeneratedFile> {
+    let features = features_list(model);
+
+    let mut subsystems = String::new();
+    let mut fields = String::new();
+    let mut init = String::new();
+    for subsystem in Subsystem::ALL {
+        let ops: Vec<_> = model.operations_in(subsystem).collect();
+        if ops.is_empty() {
+            continue;
+        }
+        let class = format!("{}Api", subsystem.type_prefix());
+        subsystems.push_str("\n\n");
+        subsystems.push_str(&render_subsystem(&class, &ops));
+        let _ = writeln!(fields, "    {}: {class}", subsystem.as_str());
+        let _ = writeln!(
+            init,
+            "        self.{} = {class}(transport)",
+            subsystem.as_str()
+        );
+    }
+
+    let client = render_template(
+        CLIENT_TEMPLATE,
+        &[
+            ("version", &model.version),
+            ("features", &features),
+            ("subsystems", subsystems.trim_end()),
+            ("client_fields", fields.trim_end()),
+            ("client_init", init.trim_end()),
+        ],
+    );
+
+    let init_py = format!(
+        "\"\"\"Hadrian Gateway API client, generated from Hadrian {}.\"\"\"\n\n\
+         from . import models\n\
+         from .client import HADRIAN_VERSION, HadrianClient, HadrianError\n\n\
+         __all__ = [\"HADRIAN_VERSION\", \"HadrianClient\", \"HadrianError\", \"models\"]\n",
+        model.version
+    );
+
+    vec![
+        GeneratedFile {
+            path: "hadrian_client/__init__.py".into(),
+            contents: init_py,
+        },
+        GeneratedFile {
+            path: "hadrian_client/models.py".into(),
+            contents: render_models(model),
+        },
+        GeneratedFile {
+            path: "hadrian_client/client.py".into(),
+            contents: client,
+        },
+        GeneratedFile {
+            path: "pyproject.toml".into(),
+            contents: render_template(PYPROJECT_TEMPLATE, &[("version", &model.version)]),
+        },
+    ]
+}
+
+fn render_models(model: &ApiModel) -> String {
+    let mut out = format!(
+        "\"\"\"Request and response types, generated from Hadrian {}. Do not edit by hand.\"\"\"\n\n\
+         from __future__ import annotations\n\n\
+         from typing import Any, Literal, Required, TypeAlias, TypedDict\n",
+        model.version
+    );
+    for (name, schema) in &model.schemas {
+        let name = pascal_case(name);
+        let props = object_properties(schema);
+        let doc = schema["description"].as_str().map(first_line);
+
+        if !is_typed_dict(schema) {
+            out.push('\n');
+            if let Some(doc) = doc.filter(|d| !d.is_empty()) {
+                let _ = writeln!(out, "# {doc}");
+            }
+            let _ = writeln!(
+                out,
+                "{name}: TypeAlias = {}",
+                double_quoted(&py_type(schema, ""))
+            );
+            continue;
+        }
+
+        out.push_str("\n\n");
+        if props.iter().all(|(prop, _, _)| is_identifier(prop)) {
+            let _ = writeln!(out, "class {name}(TypedDict, total=False):");
+            if let Some(doc) = doc.filter(|d| !d.is_empty()) {
+                let _ = writeln!(out, "    \"\"\"{}\"\"\"\n", escape_docstring(doc));
+            }
+            for (prop, prop_schema, required) in props {
+                let _ = writeln!(out, "    {prop}: {}", field_type(prop_schema, required));
+            }
+        } else {
+            // Keys that aren't valid identifiers need the functional syntax
+            let _ = writeln!(out, "{name} = TypedDict(");
+            let _ = writeln!(out, "    {},", double_quoted(&name));
+            out.push_str("    {\n");
+            for (prop, prop_schema, required) in props {
+                let _ = writeln!(
+                    out,
+                    "        {}: {},",
+                    double_quoted(prop),
+                    double_quoted(&field_type(prop_schema, required))
+                );
+            }
+            out.push_str("    },\n    total=False,\n)\n");
+        }
+    }
+    out
+}
+
+fn field_type(schema: &Value, required: bool) -> String {
+    let ty = py_type(schema, "");
+    if required {
+        format!("Required[{ty}]")
+    } else {
+        ty
+    }
+}
+
+fn render_subsystem(class: &str, ops: &[&Operation]) -> String {
+    let mut out = format!(
+        "class {class}:\n    def __init__(self, transport: _Transport) -> None:\n        self._transport = transport\n"
+    );
+    for op in ops {
+        out.push('\n');
+        render_operation(&mut out, op);
+    }
+    out
+}
+
+fn render_operation(out: &mut String, op: &Operation) {
+    let mut positional = vec!["self".to_string()];
+    let mut path = String::new();
+    for segment in path_segments(&op.path) {
+        match segment {
+            PathSegment::Literal(lit) => path.push_str(lit),
+            PathSegment::Param(name) => {
+                let ident = arg_name(name);
+                let ty = op
+                    .path_params
+                    .iter()
+                    .find(|p| p.name == name)
+                    .map(|p| py_type(&p.schema, "models."))
+                    .unwrap_or_else(|| "str".into());
+                let _ = write!(path, "{{_path({ident})}}");
+                positional.push(format!("{ident}: {ty}"));
+            }
+        }
+    }
+
+    let mut call_args = Vec::new();
+    match &op.body {
+        Some(RequestBody::Json { schema, required }) => {
+            let ty = py_type(schema, "models.");
+            if *required {
+                positional.push(format!("body: {ty}"));
+            } else {
+                positional.push(format!("body: {} = None", optional(&ty)));
+            }
+            call_args.push("json=body".to_string());
+        }
+        Some(RequestBody::Multipart) => {
+            positional.push("files: dict[str, Any]".into());
+            call_args.push("files=files".to_string());
+        }
+        None => {}
+    }
+
+    let mut keyword = Vec::new();
+    let mut query = Vec::new();
+    for param in &op.query_params {
+        let ident = arg_name(&param.name);
+        if param.required {
+            keyword.push(format!("{ident}: {}", py_type(&param.schema, "models.")));
+        } else {
+            let ty = py_type(&non_null(&param.schema), "models.");
+            keyword.push(format!("{ident}: {ty} | None = None"));
+        }
+        query.push(format!("{}: {ident}", double_quoted(&param.name)));
+    }
+    if !query.is_empty() {
+        call_args.insert(0, format!("query={{{}}}", query.join(", ")));
+    }
+
+    let ret = match &op.response {
+        ResponseBody::Json(schema) => py_type(schema, "models."),
+        ResponseBody::Binary => {
+            call_args.push("raw=True".into());
+            "bytes".into()
+        }
+        ResponseBody::Empty => "None".into(),
+    };
+
+    let mut params = positional;
+    if !keyword.is_empty() {
+        params.push("*".into());
+        params.extend(keyword);
+    }
+    let _ = writeln!(
+        out,
+        "    def {}({}) -> {ret}:",
+        method_name(&op.id),
+        params.join(", ")
+    );
+    let summary = op.summary.as_deref().map(first_line).unwrap_or_default();
+    let doc = if summary.is_empty() {
+        format!("{} {}", op.method, op.path)
+    } else {
+        format!("{summary}\n\n        {} {}\n        ", op.method, op.path)
+    };
+    let _ = writeln!(out, "        \"\"\"{}\"\"\"", escape_docstring(&doc));
+
+    let path_expr = if path.contains('{') {
+        format!("f{}", double_quoted(&path))
+    } else {
+        double_quoted(&path)
+    };
+    let mut args = vec![double_quoted(&op.method), path_expr];
+    args.extend(call_args);
+    let call = format!("self._transport.request({})", args.join(", "));
+    if matches!(op.response, ResponseBody::Empty) {
+        let _ = writeln!(out, "        {call}");
+    } else {
+        let _ = writeln!(out, "        return {call}");
+    }
+}
+
+/// Map a JSON schema to a Python type expression. `prefix` qualifies named
+/// types (`"models."` inside `client.py`).
+pub(super) fn py_type(schema: &Value, prefix: &str) -> String {
+    if let Some(name) = schema["$ref"].as_str().and_then(ref_name) {
+        return format!("{prefix}{}", pascal_case(name));
+    }
+    if let Some(value) = schema.get("const") {
+        return format!("Literal[{}]", py_literal(value));
+    }
+    if let Some(values) = schema["enum"].as_array() {
+        let values: Vec<String> = values.iter().map(py_literal).collect();
+        return format!("Literal[{}]", values.join(", "));
+    }
+    for key in ["oneOf", "anyOf"] {
+        if let Some(variants) = schema[key].as_array() {
+            return union(variants.iter().map(|v| py_type(v, prefix)));
+        }
+    }
+    if let Some(parts) = schema["allOf"].as_array() {
+        // No intersection types in Python; a lone wrapper is common though
+        return match parts.as_slice() {
+            [only] => py_type(only, prefix),
+            _ => "dict[str, Any]".into(),
+        };
+    }
+    match &schema["type"] {
+        Value::Array(types) => union(types.iter().map(|t| {
+            let mut single = schema.clone();
+            single["type"] = t.clone();
+            py_type(&single, prefix)
+        })),
+        Value::String(t) => match t.as_str() {
+            "string" => "str".into(),
+            "integer" => "int".into(),
+            "number" => "float".into(),
+            "boolean" => "bool".into(),
+            "null" => "None".into(),
+            "array" => format!("list[{}]", py_type(&schema["items"], prefix)),
+            _ => match schema.get("additionalProperties") {
+                Some(additional) if additional.is_object() && !is_typed_dict(schema) => {
+                    format!("dict[str, {}]", py_type(additional, prefix))
+                }
+                _ => "dict[str, Any]".into(),
+            },
+        },
+        _ => "Any".into(),
+    }
+}
+
+fn is_typed_dict(schema: &Value) -> bool {
+    schema.get("oneOf").is_none()
+        && schema.get("anyOf").is_none()
+        && schema.get("allOf").is_none()
+        && schema["properties"]
+            .as_object()
+            .is_some_and(|p| !p.is_empty())
+}
+
+fn union(parts: impl Iterator<Item = String>) -> String {
+    let mut seen: Vec<String> = Vec::new();
+    for part in parts {
+        if !seen.contains(&part) {
+            seen.push(part);
+        }
+    }
+    // `X | None` reads better than `None | X`
+    if let Some(pos) = seen.iter().position(|t| t == "None") {
+        let none = seen.remove(pos);
+        seen.push(none);
+    }
+    if seen.is_empty() {
+        "Any".into()
+    } else {
+        seen.join(" | ")
+    }
+}
+
+fn optional(ty: &str) -> String {
+    if ty.split(" | ").any(|t| t == "None") {
+        ty.to_string()
+    } else {
+        format!("{ty} | None")
+    }
+}
+
+fn py_literal(value: &Value) -> String {
+    match value {
+        Value::String(s) => format!("'{}'", s.replace('\\', "\\\\").replace('\'', "\\'")),
+        Value::Bool(true) => "True".into(),
+        Value::Bool(false) => "False".into(),
+        Value::Null => "None".into(),
+        other => other.to_string(),
+    }
+}
+
+fn double_quoted(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn escape_docstring(s: &str) -> String {
+    s.replace('\\', "\\\\").replace("\"\"\"", "\\\"\\\"\\\"")
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !KEYWORDS.contains(&name)
+}
+
+fn arg_name(name: &str) -> String {
+    let ident = snake_case(name);
+    if KEYWORDS.contains(&ident.as_str()) || RESERVED_ARGS.contains(&ident.as_str()) {
+        format!("{ident}_")
+    } else {
+        ident
+    }
+}
+
+fn method_name(id: &str) -> String {
+    if KEYWORDS.contains(&id) || id.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("op_{id}")
+    } else {
+        id.to_string()
+    }
+}
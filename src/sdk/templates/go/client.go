@@ -0,0 +1,118 @@
+// Package hadrian is a client for the Hadrian AI Gateway.
+//
+// Generated by `hadrian openapi --generate go` from Hadrian {{version}}.
+// Compiled features: {{features}}
+// Do not edit by hand; regenerate from the gateway build you deploy.
+package hadrian
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"fmt"
+	"io"
+	"net/http"
+	"net/url"
+	"strings"
+)
+
+// Version is the gateway version this client was generated from.
+const Version = "{{version}}"
+
+// Error is returned for non-2xx responses.
+type Error struct {
+	StatusCode int
+	Body       []byte
+}
+
+func (e *Error) Error() string {
+	return fmt.Sprintf("hadrian: request failed with status %d: %s", e.StatusCode, strings.TrimSpace(string(e.Body)))
+}
+
+// Client talks to one gateway. Endpoints are grouped by subsystem.
+type Client struct {
+	BaseURL    string
+	APIKey     string
+	HTTPClient *http.Client
+
+{{client_fields}}
+}
+
+// NewClient creates a client for the gateway at baseURL. apiKey is sent as a
+// bearer token and may be an API key or a JWT.
+func NewClient(baseURL, apiKey string) *Client {
+	c := &Client{
+		BaseURL:    strings.TrimRight(baseURL, "/"),
+		APIKey:     apiKey,
+		HTTPClient: http.DefaultClient,
+	}
+{{client_init}}
+	return c
+}
+
+type multipartBody struct {
+	reader      io.Reader
+	contentType string
+}
+
+// do sends a request. body is JSON-encoded unless it is a multipartBody; out
+// is decoded from JSON, except *[]byte which receives the raw response and nil
+// which discards it.
+func (c *Client) do(ctx context.Context, method, path string, query url.Values, body any, out any) error {
+	var reader io.Reader
+	contentType := ""
+	switch b := body.(type) {
+	case nil:
+	case multipartBody:
+		reader = b.reader
+		contentType = b.contentType
+	default:
+		buf, err := json.Marshal(b)
+		if err != nil {
+			return err
+		}
+		reader = bytes.NewReader(buf)
+		contentType = "application/json"
+	}
+
+	target := c.BaseURL + path
+	if len(query) > 0 {
+		target += "?" + query.Encode()
+	}
+	req, err := http.NewRequestWithContext(ctx, method, target, reader)
+	if err != nil {
+		return err
+	}
+	if contentType != "" {
+		req.Header.Set("Content-Type", contentType)
+	}
+	if c.APIKey != "" {
+		req.Header.Set("Authorization", "Bearer "+c.APIKey)
+	}
+
+	resp, err := c.HTTPClient.Do(req)
+	if err != nil {
+		return err
+	}
+	defer resp.Body.Close()
+	data, err := io.ReadAll(resp.Body)
+	if err != nil {
+		return err
+	}
+	if resp.StatusCode < 200 || resp.StatusCode >= 300 {
+		return &Error{StatusCode: resp.StatusCode, Body: data}
+	}
+
+	switch o := out.(type) {
+	case nil:
+		return nil
+	case *[]byte:
+		*o = data
+		return nil
+	default:
+		if len(data) == 0 {
+			return nil
+		}
+		return json.Unmarshal(data, out)
+	}
+}
@@ -0,0 +1,4 @@
+// Generated from Hadrian {{version}}. Rename the module to match where you vendor it.
+module hadrian
+
+go 1.21
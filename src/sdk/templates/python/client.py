@@ -0,0 +1,100 @@
+"""Hadrian Gateway API client.
+
+Generated by `hadrian openapi --generate python` from Hadrian {{version}}.
+Compiled features: {{features}}
+Do not edit by hand; regenerate from the gateway build you deploy.
+"""
+
+from __future__ import annotations
+
+from typing import Any
+from urllib.parse import quote
+
+import httpx
+
+from . import models
+
+__all__ = ["HADRIAN_VERSION", "HadrianClient", "HadrianError"]
+
+HADRIAN_VERSION = "{{version}}"
+
+
+class HadrianError(Exception):
+    """Raised for non-2xx responses. `body` is the parsed error payload when it is JSON."""
+
+    def __init__(self, status: int, body: Any) -> None:
+        super().__init__(f"Hadrian request failed with status {status}")
+        self.status = status
+        self.body = body
+
+
+def _path(value: Any) -> str:
+    return quote(str(value), safe="")
+
+
+class _Transport:
+    def __init__(self, http: httpx.Client) -> None:
+        self._http = http
+
+    def request(
+        self,
+        method: str,
+        path: str,
+        *,
+        query: dict[str, Any] | None = None,
+        json: Any = None,
+        files: dict[str, Any] | None = None,
+        raw: bool = False,
+    ) -> Any:
+        params = {k: v for k, v in (query or {}).items() if v is not None}
+        res = self._http.request(
+            method,
+            path,
+            params=params or None,
+            json=json if files is None else None,
+            files=files,
+        )
+        if res.is_error:
+            try:
+                body: Any = res.json()
+            except ValueError:
+                body = res.text
+            raise HadrianError(res.status_code, body)
+        if raw:
+            return res.content
+        if not res.content:
+            return None
+        return res.json()
+{{subsystems}}
+
+class HadrianClient:
+    """Entry point. Use as a context manager or call `close()` when done."""
+
+{{client_fields}}
+
+    def __init__(
+        self,
+        base_url: str,
+        api_key: str | None = None,
+        *,
+        headers: dict[str, str] | None = None,
+        timeout: float = 60.0,
+        http_client: httpx.Client | None = None,
+    ) -> None:
+        all_headers = dict(headers or {})
+        if api_key:
+            all_headers["Authorization"] = f"Bearer {api_key}"
+        self._http = http_client or httpx.Client(
+            base_url=base_url.rstrip("/"), headers=all_headers, timeout=timeout
+        )
+        transport = _Transport(self._http)
+{{client_init}}
+
+    def close(self) -> None:
+        self._http.close()
+
+    def __enter__(self) -> HadrianClient:
+        return self
+
+    def __exit__(self, *exc: object) -> None:
+        self.close()
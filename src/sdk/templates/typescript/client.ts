@@ -0,0 +1,89 @@
+// Hadrian Gateway API client.
+//
+// Generated by `hadrian openapi --generate typescript` from Hadrian {{version}}.
+// Compiled features: {{features}}
+// Do not edit by hand; regenerate from the gateway build you deploy.
+
+import type * as T from "./types";
+
+export const HADRIAN_VERSION = "{{version}}";
+
+export interface ClientOptions {
+  /** Gateway base URL, e.g. `https://gateway.example.com` */
+  baseUrl: string;
+  /** API key or JWT sent as a bearer token */
+  apiKey?: string;
+  /** Extra headers sent with every request */
+  headers?: Record<string, string>;
+  /** Custom fetch implementation (defaults to the global `fetch`) */
+  fetch?: typeof fetch;
+}
+
+/** Raised for non-2xx responses. `body` is the parsed error payload when it is JSON. */
+export class HadrianError extends Error {
+  constructor(
+    public readonly status: number,
+    public readonly body: unknown,
+  ) {
+    super(`Hadrian request failed with status ${status}`);
+    this.name = "HadrianError";
+  }
+}
+
+type QueryValue = string | number | boolean | null | undefined | Array<string | number | boolean>;
+
+interface RequestOptions {
+  query?: Record<string, QueryValue>;
+  body?: unknown;
+  responseType?: "json" | "blob" | "void";
+}
+
+export class Transport {
+  constructor(private readonly options: ClientOptions) {}
+
+  async request<R>(method: string, path: string, opts: RequestOptions = {}): Promise<R> {
+    const url = new URL(this.options.baseUrl.replace(/\/+$/, "") + path);
+    for (const [key, value] of Object.entries(opts.query ?? {})) {
+      if (value === undefined || value === null) continue;
+      if (Array.isArray(value)) {
+        for (const item of value) url.searchParams.append(key, String(item));
+      } else {
+        url.searchParams.set(key, String(value));
+      }
+    }
+
+    const headers: Record<string, string> = { ...this.options.headers };
+    if (this.options.apiKey) headers["Authorization"] = `Bearer ${this.options.apiKey}`;
+
+    let body: BodyInit | undefined;
+    if (opts.body instanceof FormData) {
+      body = opts.body;
+    } else if (opts.body !== undefined) {
+      headers["Content-Type"] = "application/json";
+      body = JSON.stringify(opts.body);
+    }
+
+    const res = await (this.options.fetch ?? fetch)(url, { method, headers, body });
+    if (!res.ok) {
+      const text = await res.text();
+      let parsed: unknown = text;
+      try {
+        parsed = JSON.parse(text);
+      } catch {
+        // Non-JSON error body; keep the raw text
+      }
+      throw new HadrianError(res.status, parsed);
+    }
+    if (opts.responseType === "void") return undefined as R;
+    if (opts.responseType === "blob") return (await res.blob()) as R;
+    return (await res.json()) as R;
+  }
+}
+{{subsystems}}
+export class HadrianClient {
+{{client_fields}}
+  constructor(options: ClientOptions) {
+    const transport = new Transport(options);
+{{client_init}}
+  }
+}
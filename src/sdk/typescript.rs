@@ -0,0 +1,398 @@
+//! TypeScript SDK: `types.ts` interfaces plus a fetch-based `client.ts`.
+
+use std::fmt::Write;
+
+use serde_json::Value;
+
+use super::{
+    ApiModel, GeneratedFile, Operation, PathSegment, RequestBody, ResponseBody, Subsystem,
+    camel_case, features_list, first_line, object_properties, pascal_case, path_segments, ref_name,
+    render_template,
+};
+
+const CLIENT_TEMPLATE: &str = include_str!("templates/typescript/client.ts");
+const PACKAGE_TEMPLATE: &str = include_str!("templates/typescript/package.json");
+
+const RESERVED: &[&str] = &[
+    "body",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "import",
+    "in",
+    "instanceof",
+    "new",
+    "null",
+    "query",
+    "return",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+];
+
+pub(super) fn render(model: &ApiModel) -> Vec<GeneratedFile> {
+    let features = features_list(model);
+
+    let mut subsystems = String::new();
+    let mut fields = String::new();
+    let mut init = String::new();
+    for subsystem in Subsystem::ALL {
+        let ops: Vec<_> = model.operations_in(subsystem).collect();
+        if ops.is_empty() {
+            continue;
+        }
+        let class = format!("{}Api", subsystem.type_prefix());
+        subsystems.push('\n');
+        subsystems.push_str(&render_subsystem(&class, &ops));
+        let _ = writeln!(fields, "  readonly {}: {class};", subsystem.as_str());
+        let _ = writeln!(
+            init,
+            "    this.{} = new {class}(transport);",
+            subsystem.as_str()
+        );
+    }
+
+    let client = render_template(
+        CLIENT_TEMPLATE,
+        &[
+            ("version", &model.version),
+            ("features", &features),
+            ("subsystems", &subsystems),
+            ("client_fields", fields.trim_end()),
+            ("client_init", init.trim_end()),
+        ],
+    );
+
+    vec![
+        GeneratedFile {
+            path: "types.ts".into(),
+            contents: render_types(model),
+        },
+        GeneratedFile {
+            path: "client.ts".into(),
+            contents: client,
+        },
+        GeneratedFile {
+            path: "index.ts".into(),
+            contents: "export * from \"./client\";\nexport type * from \"./types\";\n".into(),
+        },
+        GeneratedFile {
+            path: "package.json".into(),
+            contents: render_template(PACKAGE_TEMPLATE, &[("version", &model.version)]),
+        },
+    ]
+}
+
+fn render_types(model: &ApiModel) -> String {
+    let mut out = format!(
+        "// Generated by `hadrian openapi --generate typescript` from Hadrian {}.\n\
+         // Do not edit by hand.\n",
+        model.version
+    );
+    for (name, schema) in &model.schemas {
+        out.push('\n');
+        write_doc(&mut out, "", schema["description"].as_str());
+        let name = pascal_case(name);
+        if is_interface(schema) {
+            let _ = writeln!(out, "export interface {name} {{");
+            for (prop, prop_schema, required) in object_properties(schema) {
+                write_doc(&mut out, "  ", prop_schema["description"].as_str());
+                let optional = if required { "" } else { "?" };
+                let _ = writeln!(
+                    out,
+                    "  {}{optional}: {};",
+                    property_key(prop),
+                    ts_type(prop_schema, "")
+                );
+            }
+            if has_additional_properties(schema) {
+                out.push_str("  [key: string]: unknown;\n");
+            }
+            out.push_str("}\n");
+        } else {
+            let _ = writeln!(out, "export type {name} = {};", ts_type(schema, ""));
+        }
+    }
+    out
+}
+
+fn render_subsystem(class: &str, ops: &[&Operation]) -> String {
+    let mut out = format!(
+        "export class {class} {{\n  constructor(private readonly transport: Transport) {{}}\n"
+    );
+    for op in ops {
+        out.push('\n');
+        render_operation(&mut out, op);
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn render_operation(out: &mut String, op: &Operation) {
+    // (declaration, optional)
+    let mut args: Vec<(String, bool)> = Vec::new();
+    let mut path = String::new();
+    for segment in path_segments(&op.path) {
+        match segment {
+            PathSegment::Literal(lit) => path.push_str(lit),
+            PathSegment::Param(name) => {
+                let ident = ident(name);
+                let schema = op
+                    .path_params
+                    .iter()
+                    .find(|p| p.name == name)
+                    .map(|p| ts_type(&p.schema, "T."))
+                    .unwrap_or_else(|| "string".into());
+                let _ = write!(path, "${{encodeURIComponent(String({ident}))}}");
+                args.push((format!("{ident}: {schema}"), false));
+            }
+        }
+    }
+
+    let mut options = Vec::new();
+    match &op.body {
+        Some(RequestBody::Json { schema, required }) => {
+            let optional = !required;
+            let marker = if optional { "?" } else { "" };
+            args.push((format!("body{marker}: {}", ts_type(schema, "T.")), optional));
+            options.push("body");
+        }
+        Some(RequestBody::Multipart) => {
+            args.push(("body: FormData".into(), false));
+            options.push("body");
+        }
+        None => {}
+    }
+
+    if !op.query_params.is_empty() {
+        let required = op.query_params.iter().any(|p| p.required);
+        let fields: Vec<String> = op
+            .query_params
+            .iter()
+            .map(|p| {
+                let marker = if p.required { "" } else { "?" };
+                format!(
+                    "{}{marker}: {}",
+                    property_key(&p.name),
+                    ts_type(&p.schema, "T.")
+                )
+            })
+            .collect();
+        let marker = if required { "" } else { "?" };
+        args.push((
+            format!("query{marker}: {{ {} }}", fields.join("; ")),
+            !required,
+        ));
+        options.push("query");
+    }
+
+    // Required parameters can't follow optional ones
+    args.sort_by_key(|(_, optional)| *optional);
+
+    let (ret, response_type) = match &op.response {
+        ResponseBody::Json(schema) => (ts_type(schema, "T."), None),
+        ResponseBody::Binary => ("Blob".to_string(), Some("\"blob\"")),
+        ResponseBody::Empty => ("void".to_string(), Some("\"void\"")),
+    };
+    let mut opts: Vec<String> = options.iter().map(|o| o.to_string()).collect();
+    if let Some(response_type) = response_type {
+        opts.push(format!("responseType: {response_type}"));
+    }
+    let opts = if opts.is_empty() {
+        String::new()
+    } else {
+        format!(", {{ {} }}", opts.join(", "))
+    };
+
+    let summary = op.summary.as_deref().map(first_line).unwrap_or_default();
+    let _ = writeln!(
+        out,
+        "  /** {}{}{} {} */",
+        escape_comment(summary),
+        if summary.is_empty() { "" } else { " — " },
+        op.method,
+        op.path
+    );
+    let args: Vec<_> = args.into_iter().map(|(decl, _)| decl).collect();
+    let _ = writeln!(
+        out,
+        "  async {}({}): Promise<{ret}> {{",
+        camel_case(&op.id),
+        args.join(", ")
+    );
+    let _ = writeln!(
+        out,
+        "    return this.transport.request<{ret}>(\"{}\", `{path}`{opts});",
+        op.method
+    );
+    out.push_str("  }\n");
+}
+
+/// Map a JSON schema to a TypeScript type. `prefix` qualifies named types
+/// (`"T."` inside `client.ts`).
+pub(super) fn ts_type(schema: &Value, prefix: &str) -> String {
+    if let Some(name) = schema["$ref"].as_str().and_then(ref_name) {
+        return format!("{prefix}{}", pascal_case(name));
+    }
+    if let Some(value) = schema.get("const") {
+        return value.to_string();
+    }
+    if let Some(values) = schema["enum"].as_array() {
+        return union(values.iter().map(Value::to_string));
+    }
+    for key in ["oneOf", "anyOf"] {
+        if let Some(variants) = schema[key].as_array() {
+            return union(variants.iter().map(|v| ts_type(v, prefix)));
+        }
+    }
+    if let Some(parts) = schema["allOf"].as_array() {
+        let parts: Vec<String> = parts
+            .iter()
+            .map(|p| wrap_union(ts_type(p, prefix)))
+            .collect();
+        return parts.join(" & ");
+    }
+    match &schema["type"] {
+        Value::Array(types) => union(types.iter().map(|t| {
+            let mut single = schema.clone();
+            single["type"] = t.clone();
+            ts_type(&single, prefix)
+        })),
+        Value::String(t) => match t.as_str() {
+            "string" => "string".into(),
+            "integer" | "number" => "number".into(),
+            "boolean" => "boolean".into(),
+            "null" => "null".into(),
+            "array" => format!("Array<{}>", ts_type(&schema["items"], prefix)),
+            _ => inline_object(schema, prefix),
+        },
+        _ if schema.get("properties").is_some() => inline_object(schema, prefix),
+        _ => "unknown".into(),
+    }
+}
+
+fn inline_object(schema: &Value, prefix: &str) -> String {
+    let props = object_properties(schema);
+    if props.is_empty() {
+        return match schema.get("additionalProperties") {
+            Some(additional) if additional.is_object() => {
+                format!("Record<string, {}>", ts_type(additional, prefix))
+            }
+            _ => "Record<string, unknown>".into(),
+        };
+    }
+    let fields: Vec<String> = props
+        .into_iter()
+        .map(|(name, prop, required)| {
+            let marker = if required { "" } else { "?" };
+            format!("{}{marker}: {}", property_key(name), ts_type(prop, prefix))
+        })
+        .collect();
+    format!("{{ {} }}", fields.join("; "))
+}
+
+fn union(parts: impl Iterator<Item = String>) -> String {
+    let mut seen = Vec::new();
+    for part in parts {
+        if !seen.contains(&part) {
+            seen.push(part);
+        }
+    }
+    match seen.len() {
+        0 => "unknown".into(),
+        _ => seen.join(" | "),
+    }
+}
+
+fn wrap_union(ty: String) -> String {
+    if ty.contains(" | ") {
+        format!("({ty})")
+    } else {
+        ty
+    }
+}
+
+fn is_interface(schema: &Value) -> bool {
+    schema.get("oneOf").is_none()
+        && schema.get("anyOf").is_none()
+        && schema.get("allOf").is_none()
+        && schema["properties"]
+            .as_object()
+            .is_some_and(|p| !p.is_empty())
+}
+
+fn has_additional_properties(schema: &Value) -> bool {
+    match schema.get("additionalProperties") {
+        Some(Value::Bool(allowed)) => *allowed,
+        Some(Value::Object(_)) => true,
+        _ => false,
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+fn property_key(name: &str) -> String {
+    if is_identifier(name) {
+        name.to_string()
+    } else {
+        Value::String(name.to_string()).to_string()
+    }
+}
+
+fn ident(name: &str) -> String {
+    let ident = camel_case(name);
+    if RESERVED.contains(&ident.as_str()) {
+        format!("{ident}Param")
+    } else {
+        ident
+    }
+}
+
+fn escape_comment(text: &str) -> String {
+    text.replace("*/", "*\\/")
+}
+
+fn write_doc(out: &mut String, indent: &str, description: Option<&str>) {
+    let Some(description) = description.map(str::trim).filter(|d| !d.is_empty()) else {
+        return;
+    };
+    let description = escape_comment(description);
+    if !description.contains('\n') {
+        let _ = writeln!(out, "{indent}/** {description} */");
+        return;
+    }
+    let _ = writeln!(out, "{indent}/**");
+    for line in description.lines() {
+        let _ = writeln!(out, "{indent} * {line}");
+    }
+    let _ = writeln!(out, "{indent} */");
+}
@@ -4,7 +4,7 @@ use chrono::{DateTime, Duration, Utc};
 use uuid::Uuid;
 
 use crate::{
-    db::{DbPool, DbResult, ListParams},
+    db::{Cursor, CursorDirection, DbPool, DbResult, ListParams, PageCursors},
     models::{
         AccessGrantHistoryEntry, AccessInventoryResponse, AccessInventorySummary, ApiKeySummary,
         AuditActorType, AuditLogQuery, NeverActiveUserEntry, OrgAccessEntry,
@@ -14,6 +14,7 @@ use crate::{
         UserAccessInventoryEntry, UserAccessOrgEntry, UserAccessProjectEntry, UserAccessSummary,
         UserAccessSummaryResponse,
     },
+    openapi::PaginationMeta,
 };
 
 /// Service layer for access review operations
@@ -31,32 +32,53 @@ impl AccessReviewService {
     ///
     /// This returns a comprehensive view of all users and their access rights
     /// across organizations and projects, including API key counts and last activity.
+    ///
+    /// Users are paged by keyset on `(created_at, id)` via `params.cursor`. The
+    /// legacy `offset` mode is used only when no cursor is given; it still
+    /// returns a `next_cursor` so clients can switch to cursors mid-scan.
     pub async fn get_access_inventory(
         &self,
         org_filter: Option<Uuid>,
-        limit: i64,
-        offset: i64,
+        params: ListParams,
+        offset: Option<i64>,
     ) -> DbResult<AccessInventoryResponse> {
         let generated_at = Utc::now();
+        let limit = params.limit.unwrap_or(100);
 
         // Get total user count
         let total_users = self.db.users().count(false).await?;
 
-        // Get all users (paginated)
-        let users_result = self
-            .db
-            .users()
-            .list(ListParams {
-                limit: Some(limit),
-                include_deleted: false,
-                ..Default::default()
-            })
-            .await?;
+        // Get one page of users
+        let (users, has_more, cursors) = match offset.filter(|o| *o > 0) {
+            Some(offset) if params.cursor.is_none() => {
+                let result = self
+                    .db
+                    .users()
+                    .list(ListParams {
+                        limit: Some(offset + limit),
+                        ..params
+                    })
+                    .await?;
+                let users: Vec<User> = result.items.into_iter().skip(offset as usize).collect();
+                let cursors = PageCursors::from_items(
+                    &users,
+                    result.has_more,
+                    CursorDirection::Forward,
+                    None,
+                    |u| Cursor::new(u.created_at, u.id),
+                );
+                (users, result.has_more, cursors)
+            }
+            _ => {
+                let result = self.db.users().list(params).await?;
+                (result.items, result.has_more, result.cursors)
+            }
+        };
 
         // Build user access entries
-        let mut user_entries = Vec::with_capacity(users_result.items.len());
+        let mut user_entries = Vec::with_capacity(users.len());
 
-        for user in users_result.items.into_iter().skip(offset as usize) {
+        for user in users {
             // Get org memberships
             let org_memberships = self
                 .db
@@ -140,6 +162,12 @@ impl AccessReviewService {
             total_users,
             users: user_entries,
             summary,
+            pagination: PaginationMeta::with_cursors(
+                limit,
+                has_more,
+                cursors.next.map(|c| c.encode()),
+                cursors.prev.map(|c| c.encode()),
+            ),
         })
     }
 
@@ -0,0 +1,416 @@
+//! Agent runs executed by the gateway.
+//!
+//! An agent is a model with a system prompt, server-executed tools and the
+//! vector stores it searches. `POST /v1/agents/{agent_id}/runs` turns an
+//! agent and the caller's input into a streaming Responses request and sends
+//! it through the shared `apply_streaming_pipeline`, so the tool-use loop
+//! runs server-side, bounded by the agent's `max_iterations`.
+//!
+//! [`record_run`] drains the pipeline's stream: every output item becomes a
+//! step of the run, and the run is finished with its final answer, the tokens
+//! used across all turns and their cost. Model usage is logged with the run
+//! ID as its request ID, so usage records can be traced back to the run.
+
+use axum::body::Body;
+use bytes::Bytes;
+use futures_util::StreamExt;
+use serde_json::{Value, json};
+use tokio::sync::mpsc;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::{
+    api_types::{
+        CreateResponsesPayload,
+        responses::{FileSearchTool, FileSearchToolType, ResponsesToolDefinition},
+    },
+    config::{FeaturesConfig, ShellRuntimeConfig},
+    db::DbPool,
+    models::{Agent, AgentRun, AgentRunOutcome, AgentRunStatus, UsageLogEntry},
+    pricing::{CostPricingSource, dollars_to_microcents},
+    streaming::SseBuffer,
+};
+
+/// Channel a streaming run's events are forwarded to.
+pub type RunEventSender = mpsc::Sender<Result<Bytes, std::io::Error>>;
+
+/// Check that an agent only uses tools the gateway executes itself: there's
+/// no client in the loop to run anything else. Function tools must name a
+/// `[[features.server_tools.http_tools]]` entry or one of `org_tools`, the
+/// HTTP tools the agent's organization registered.
+pub fn validate_tools(
+    features: &FeaturesConfig,
+    org_tools: &[String],
+    tools: &[ResponsesToolDefinition],
+) -> Result<(), String> {
+    for tool in tools {
+        match tool {
+            ResponsesToolDefinition::FileSearch(_) => {
+                if !features.file_search.as_ref().is_some_and(|c| c.enabled) {
+                    return Err("file_search is not enabled on this gateway".to_string());
+                }
+            }
+            tool if tool.is_web_search() => {
+                if features.web_search.is_none() {
+                    return Err("web_search is not enabled on this gateway".to_string());
+                }
+            }
+            ResponsesToolDefinition::Shell(_) => {
+                if matches!(
+                    features.shell,
+                    ShellRuntimeConfig::None
+                        | ShellRuntimeConfig::PassthroughOpenAI
+                        | ShellRuntimeConfig::ClientPassthrough
+                ) {
+                    return Err("shell needs a sandbox runtime on this gateway".to_string());
+                }
+            }
+            ResponsesToolDefinition::Function(function) => {
+                if features.server_tools.http_tool(&function.name).is_none()
+                    && !org_tools.contains(&function.name)
+                {
+                    return Err(format!(
+                        "function tool '{}' is not a configured or registered HTTP tool",
+                        function.name
+                    ));
+                }
+            }
+            _ => {
+                return Err(
+                    "agents support file_search, web_search, shell and HTTP tools only".to_string(),
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Build the Responses request a run of `agent` sends. The agent's vector
+/// stores are searched through a `file_search` tool, added unless the agent
+/// already defines one.
+pub fn build_payload(
+    agent: &Agent,
+    input: &Value,
+) -> Result<CreateResponsesPayload, serde_json::Error> {
+    let mut payload: CreateResponsesPayload = serde_json::from_value(json!({
+        "model": agent.model,
+        "input": input,
+        "stream": true,
+    }))?;
+    payload.instructions = agent.instructions.clone();
+
+    let mut tools = agent.tools.clone();
+    if !agent.vector_store_ids.is_empty() && !tools.iter().any(|t| t.is_file_search()) {
+        tools.push(ResponsesToolDefinition::FileSearch(FileSearchTool {
+            type_: FileSearchToolType::FileSearch,
+            vector_store_ids: agent.vector_store_ids.clone(),
+            max_num_results: None,
+            ranking_options: None,
+            filters: None,
+            cache_control: None,
+        }));
+    }
+    if !tools.is_empty() {
+        payload.tools = Some(tools);
+    }
+    Ok(payload)
+}
+
+/// Format an event of the run itself, as opposed to the Responses events of
+/// its turns.
+pub fn run_event(event_type: &str, run: &AgentRun) -> Bytes {
+    let data = json!({ "type": event_type, "run": run });
+    Bytes::from(format!("event: {event_type}\ndata: {data}\n\n"))
+}
+
+/// Usage record for a run's model tokens, filled in as the stream is
+/// metered. The run ID is the request ID so the run's usage can be found.
+pub fn usage_entry(run: &AgentRun, team_id: Option<Uuid>) -> UsageLogEntry {
+    UsageLogEntry {
+        request_id: run.id.to_string(),
+        api_key_id: run.api_key_id,
+        user_id: run.user_id,
+        org_id: Some(run.org_id),
+        project_id: run.project_id,
+        team_id,
+        service_account_id: run.service_account_id,
+        model: run.model.clone(),
+        provider: run.provider.clone(),
+        input_tokens: 0,
+        output_tokens: 0,
+        cost_microcents: None,
+        http_referer: None,
+        request_at: run.created_at,
+        streamed: true,
+        cached_tokens: 0,
+        reasoning_tokens: 0,
+        finish_reason: None,
+        latency_ms: None,
+        cancelled: false,
+        status_code: None,
+        pricing_source: CostPricingSource::None,
+        image_count: None,
+        audio_seconds: None,
+        character_count: None,
+        provider_source: None,
+        record_type: "model".to_string(),
+        tool_name: None,
+        tool_query: None,
+        tool_url: None,
+        tool_bytes_fetched: None,
+        tool_results_count: None,
+        tool_runtime_seconds: None,
+        tool_exit_code: None,
+        jwt_subject: None,
+        error_code: None,
+        structured_output_repairs: None,
+        context_original_tokens: None,
+        context_compressed_tokens: None,
+        degraded_from_model: None,
+        degradation_reason: None,
+        ttft_ms: None,
+        inter_token_latency_ms: None,
+        output_tokens_per_second: None,
+        idempotency_key: None,
+        provider_attempts: None,
+        suspected_duplicate_attempts: None,
+        tags: None,
+    }
+}
+
+/// What a run's stream has produced so far.
+#[derive(Debug, Default)]
+struct RunProgress {
+    terminal: Option<(AgentRunStatus, Option<String>)>,
+    output_text: Option<String>,
+    input_tokens: i64,
+    output_tokens: i64,
+    cost_dollars: Option<f64>,
+}
+
+impl RunProgress {
+    /// Take in a Responses event, returning the output item it completes.
+    fn observe(&mut self, event: &Value) -> Option<Value> {
+        match event.get("type").and_then(Value::as_str)? {
+            "response.output_item.done" => {
+                let item = event.get("item")?;
+                if let Some(text) = message_text(item) {
+                    self.output_text = Some(text);
+                }
+                Some(item.clone())
+            }
+            event_type @ ("response.completed" | "response.incomplete" | "response.failed") => {
+                let response = event.get("response");
+                if let Some(usage) = response.and_then(|r| r.get("usage")) {
+                    let tokens = |key| usage.get(key).and_then(Value::as_i64).unwrap_or(0);
+                    self.input_tokens += tokens("input_tokens");
+                    self.output_tokens += tokens("output_tokens");
+                    if let Some(cost) = usage.get("cost").and_then(Value::as_f64) {
+                        *self.cost_dollars.get_or_insert(0.0) += cost;
+                    }
+                }
+                self.terminal = Some(match event_type {
+                    "response.completed" => (AgentRunStatus::Completed, None),
+                    "response.incomplete" => (AgentRunStatus::Incomplete, None),
+                    _ => (
+                        AgentRunStatus::Failed,
+                        response
+                            .and_then(|r| r.pointer("/error/message"))
+                            .and_then(Value::as_str)
+                            .map(str::to_owned)
+                            .or_else(|| Some("The model request failed".to_string())),
+                    ),
+                });
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn into_outcome(self, stream_error: Option<String>) -> AgentRunOutcome {
+        let (status, error) = match (self.terminal, stream_error) {
+            (_, Some(e)) => (AgentRunStatus::Failed, Some(e)),
+            (Some(terminal), None) => terminal,
+            (None, None) => (
+                AgentRunStatus::Failed,
+                Some("The stream ended before the run finished".to_string()),
+            ),
+        };
+        AgentRunOutcome {
+            status,
+            output_text: self.output_text,
+            error,
+            input_tokens: self.input_tokens,
+            output_tokens: self.output_tokens,
+            cost_microcents: self.cost_dollars.map(dollars_to_microcents),
+        }
+    }
+}
+
+/// Text of a completed `message` output item.
+fn message_text(item: &Value) -> Option<String> {
+    if item.get("type").and_then(Value::as_str) != Some("message") {
+        return None;
+    }
+    let text: String = item
+        .get("content")?
+        .as_array()?
+        .iter()
+        .filter(|part| part.get("type").and_then(Value::as_str) == Some("output_text"))
+        .filter_map(|part| part.get("text").and_then(Value::as_str))
+        .collect();
+    (!text.is_empty()).then_some(text)
+}
+
+/// Drain a run's pipeline stream, recording each output item as a step and
+/// finishing the run once the stream ends. Events are forwarded to `events`
+/// while it's open; the run carries on if the client goes away.
+pub async fn record_run(
+    db: &DbPool,
+    run: AgentRun,
+    body: Body,
+    events: Option<RunEventSender>,
+) -> AgentRun {
+    let repo = db.agents();
+    let mut events = events;
+    let mut progress = RunProgress::default();
+    let mut buffer = SseBuffer::new();
+    let mut sequence = 0;
+    let mut stream_error = None;
+
+    let mut stream = body.into_data_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = match chunk {
+            Ok(chunk) => chunk,
+            Err(e) => {
+                warn!(run_id = %run.id, error = %e, "Stream error during agent run");
+                stream_error = Some(format!("The run's stream failed: {e}"));
+                break;
+            }
+        };
+        buffer.extend(&chunk);
+        for raw in buffer.extract_complete_events() {
+            let data = std::str::from_utf8(&raw).ok().and_then(|text| {
+                text.lines()
+                    .find_map(|line| line.strip_prefix("data:").map(str::trim))
+            });
+            // The run's own terminal event is sent once the run is stored
+            if data == Some("[DONE]") {
+                continue;
+            }
+            if let Some(tx) = &events
+                && tx.send(Ok(raw.clone())).await.is_err()
+            {
+                events = None;
+            }
+            let Some(event) = data.and_then(|d| serde_json::from_str::<Value>(d).ok()) else {
+                continue;
+            };
+            if let Some(item) = progress.observe(&event) {
+                let step_type = item
+                    .get("type")
+                    .and_then(Value::as_str)
+                    .unwrap_or("unknown");
+                if let Err(e) = repo.add_step(run.id, sequence, step_type, &item).await {
+                    warn!(run_id = %run.id, error = %e, "Failed to record agent run step");
+                }
+                sequence += 1;
+            }
+        }
+    }
+
+    let outcome = progress.into_outcome(stream_error);
+    let finished = match repo.finish_run(run.id, &outcome).await {
+        Ok(finished) => finished,
+        Err(e) => {
+            warn!(run_id = %run.id, error = %e, "Failed to finish agent run");
+            AgentRun {
+                status: outcome.status,
+                output_text: outcome.output_text,
+                error: outcome.error,
+                input_tokens: outcome.input_tokens,
+                output_tokens: outcome.output_tokens,
+                cost_microcents: outcome.cost_microcents,
+                ..run
+            }
+        }
+    };
+
+    if let Some(tx) = events {
+        let _ = tx
+            .send(Ok(run_event("agent.run.finished", &finished)))
+            .await;
+        let _ = tx.send(Ok(Bytes::from_static(b"data: [DONE]\n\n"))).await;
+    }
+    finished
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool(value: Value) -> ResponsesToolDefinition {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn validate_tools_rejects_client_side_tools() {
+        let features = FeaturesConfig::default();
+        assert!(validate_tools(&features, &[], &[]).is_ok());
+        assert!(validate_tools(&features, &[], &[tool(json!({"type": "shell"}))]).is_err());
+        let lookup = tool(json!({"type": "function", "name": "lookup_order"}));
+        let err = validate_tools(&features, &[], std::slice::from_ref(&lookup)).unwrap_err();
+        assert!(err.contains("lookup_order"));
+        assert!(validate_tools(&features, &["lookup_order".to_string()], &[lookup]).is_ok());
+        assert!(
+            validate_tools(
+                &features,
+                &[],
+                &[tool(json!({
+                    "type": "mcp",
+                    "server_label": "docs",
+                    "server_url": "https://example.com/mcp"
+                }))]
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn progress_collects_steps_usage_and_answer() {
+        let mut progress = RunProgress::default();
+        let call = json!({"type": "file_search_call", "id": "fs_1"});
+        assert_eq!(
+            progress.observe(&json!({"type": "response.output_item.done", "item": call})),
+            Some(call)
+        );
+        progress.observe(&json!({
+            "type": "response.output_item.done",
+            "item": {
+                "type": "message",
+                "content": [{"type": "output_text", "text": "Within 30 days."}]
+            }
+        }));
+        assert_eq!(
+            progress.observe(&json!({"type": "response.output_text.delta", "delta": "x"})),
+            None
+        );
+        progress.observe(&json!({
+            "type": "response.completed",
+            "response": {"usage": {"input_tokens": 100, "output_tokens": 20, "cost": 0.0015}}
+        }));
+
+        let outcome = progress.into_outcome(None);
+        assert_eq!(outcome.status, AgentRunStatus::Completed);
+        assert_eq!(outcome.output_text.as_deref(), Some("Within 30 days."));
+        assert_eq!((outcome.input_tokens, outcome.output_tokens), (100, 20));
+        assert_eq!(outcome.cost_microcents, Some(1500));
+    }
+
+    #[test]
+    fn progress_without_terminal_event_fails() {
+        let outcome = RunProgress::default().into_outcome(None);
+        assert_eq!(outcome.status, AgentRunStatus::Failed);
+        assert!(outcome.error.is_some());
+    }
+}
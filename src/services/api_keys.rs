@@ -1,11 +1,14 @@
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
 use chrono::{Duration, Utc};
 use uuid::Uuid;
 
 use crate::{
-    db::{DbPool, DbResult, ListParams, ListResult},
-    models::{ApiKey, ApiKeyWithOwner, CreateApiKey, CreatedApiKey, generate_api_key_with_prefix},
+    db::{DbPool, DbResult, ListParams, ListResult, MAX_LIST_LIMIT},
+    models::{
+        ApiKey, ApiKeyHasher, ApiKeyOwner, ApiKeyWithOwner, CreateApiKey, CreatedApiKey,
+        generate_api_key_with_prefix,
+    },
 };
 
 /// Service layer for API key operations
@@ -19,11 +22,20 @@ impl ApiKeyService {
         Self { db }
     }
 
-    /// Create a new API key with the given prefix
+    /// Create a new API key with the given prefix, hashed with `hasher`
     /// Returns both the stored key and the raw key (only shown once)
-    pub async fn create(&self, input: CreateApiKey, prefix: &str) -> DbResult<CreatedApiKey> {
-        let (raw_key, key_hash) = generate_api_key_with_prefix(prefix);
-        let api_key = self.db.api_keys().create(input, &key_hash).await?;
+    pub async fn create(
+        &self,
+        input: CreateApiKey,
+        prefix: &str,
+        hasher: &ApiKeyHasher,
+    ) -> DbResult<CreatedApiKey> {
+        let (raw_key, key_hash) = generate_api_key_with_prefix(prefix, hasher);
+        let api_key = self
+            .db
+            .api_keys()
+            .create_with_hash(input, &key_hash)
+            .await?;
         Ok(CreatedApiKey {
             api_key,
             key: raw_key,
@@ -104,6 +116,15 @@ impl ApiKeyService {
         self.db.api_keys().revoke(id).await
     }
 
+    /// Replace an API key's labels
+    pub async fn set_labels(
+        &self,
+        id: Uuid,
+        labels: &BTreeMap<String, String>,
+    ) -> DbResult<ApiKey> {
+        self.db.api_keys().set_labels(id, labels).await
+    }
+
     /// Update the last used timestamp for an API key
     pub async fn update_last_used(&self, id: Uuid) -> DbResult<()> {
         self.db.api_keys().update_last_used(id).await
@@ -129,6 +150,40 @@ impl ApiKeyService {
             .await
     }
 
+    /// List every API key belonging to `owner`, revoked keys included.
+    ///
+    /// Walks all pages, so callers should filter the result rather than
+    /// expect it to be small.
+    pub async fn list_all_by_owner(&self, owner: &ApiKeyOwner) -> DbResult<Vec<ApiKey>> {
+        let repo = self.db.api_keys();
+        let mut keys = Vec::new();
+        let mut cursor = None;
+        loop {
+            let params = ListParams {
+                limit: Some(MAX_LIST_LIMIT),
+                cursor,
+                ..Default::default()
+            };
+            let page = match owner {
+                ApiKeyOwner::Organization { org_id } => repo.list_by_org(*org_id, params).await?,
+                ApiKeyOwner::Team { team_id } => repo.list_by_team(*team_id, params).await?,
+                ApiKeyOwner::Project { project_id } => {
+                    repo.list_by_project(*project_id, params).await?
+                }
+                ApiKeyOwner::User { user_id } => repo.list_by_user(*user_id, params).await?,
+                ApiKeyOwner::ServiceAccount { service_account_id } => {
+                    repo.list_by_service_account(*service_account_id, params)
+                        .await?
+                }
+            };
+            keys.extend(page.items);
+            match page.cursors.next {
+                Some(next) if page.has_more => cursor = Some(next),
+                _ => return Ok(keys),
+            }
+        }
+    }
+
     /// Count API keys for a service account
     pub async fn count_by_service_account(
         &self,
@@ -194,6 +249,7 @@ impl ApiKeyService {
         old_key_id: Uuid,
         grace_period_seconds: u64,
         prefix: &str,
+        hasher: &ApiKeyHasher,
     ) -> DbResult<CreatedApiKey> {
         // Get the old key to copy its settings
         let old_key = self
@@ -236,13 +292,13 @@ impl ApiKeyService {
         };
 
         // Generate new key
-        let (raw_key, key_hash) = generate_api_key_with_prefix(prefix);
+        let (raw_key, key_hash) = generate_api_key_with_prefix(prefix, hasher);
 
         // Perform the rotation
         let new_key = self
             .db
             .api_keys()
-            .rotate(old_key_id, new_key_input, &key_hash, grace_until)
+            .rotate_with_hash(old_key_id, new_key_input, &key_hash, grace_until)
             .await?;
 
         Ok(CreatedApiKey {
@@ -29,6 +29,26 @@ pub mod auth_events {
     pub const BOOTSTRAP_LOGIN_FAILED: &str = "auth.bootstrap.login_failed";
     /// Logout (any provider)
     pub const LOGOUT: &str = "auth.logout";
+    /// Session evicted by `max_concurrent_sessions` when a newer login arrived
+    pub const SESSION_EVICTED: &str = "auth.session.evicted";
+    /// TOTP code accepted for admin step-up authentication
+    pub const STEP_UP_VERIFIED: &str = "auth.step_up.verified";
+    /// TOTP code rejected for admin step-up authentication
+    pub const STEP_UP_FAILED: &str = "auth.step_up.failed";
+    /// TOTP authenticator enrollment confirmed
+    pub const TOTP_ENROLLED: &str = "auth.totp.enrolled";
+    /// TOTP authenticator removed
+    pub const TOTP_REMOVED: &str = "auth.totp.removed";
+    /// Admin session created by signing in with a passkey
+    pub const PASSKEY_LOGIN: &str = "auth.passkey.login";
+    /// Passkey accepted as a second factor for an existing session
+    pub const PASSKEY_VERIFIED: &str = "auth.passkey.verified";
+    /// Passkey assertion rejected (sign-in or second factor)
+    pub const PASSKEY_FAILED: &str = "auth.passkey.failed";
+    /// Passkey registered
+    pub const PASSKEY_REGISTERED: &str = "auth.passkey.registered";
+    /// Passkey removed
+    pub const PASSKEY_REMOVED: &str = "auth.passkey.removed";
 }
 
 /// Parameters for logging an auth event
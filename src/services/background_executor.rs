@@ -21,6 +21,7 @@ use crate::{
     AppState,
     api_types::CreateResponsesPayload,
     db::repos::{ResponseCompletion, ResponseRecord, ResponseStatus},
+    providers::Tenant,
     routes::execution::{ExecutionResult, ResponsesExecutor, execute_with_fallback},
     routing::{resolver, route_models_extended},
     services::{
@@ -185,6 +186,7 @@ pub async fn execute_persisted_response(
         state.db.as_ref(),
         state.cache.as_ref(),
         state.secrets.as_ref(),
+        &state.provider_settings,
         None, // background runs without an auth extension; principal already on the row
     )
     .await
@@ -262,6 +264,14 @@ pub async fn execute_persisted_response(
         .await
         .map_err(|e| BackgroundExecuteError::BadPayload(format!("skill resolution failed: {e}")))?;
 
+    let org_http_tools = crate::services::http_tool::resolve_org_http_tools(
+        &state,
+        &mut payload,
+        Some(record.org_id),
+    )
+    .await
+    .map_err(|e| BackgroundExecuteError::Execution(format!("HTTP tool lookup failed: {e}")))?;
+
     // Final resolved env (re-do after potential payload changes).
     let resolved_shell_env = {
         let request_env = first_shell_environment(&payload);
@@ -301,9 +311,19 @@ pub async fn execute_persisted_response(
 
     // Gateway-side compaction for non-OpenAI providers. Best-effort:
     // an error here means the original payload still flows through.
-    if let Err(e) =
-        crate::services::compactor::apply_gateway_compaction(&state, &provider_config, &mut payload)
-            .await
+    let tenant = Tenant {
+        org_id: Some(record.org_id),
+        project_id: record.project_id,
+        api_key_id: record.api_key_id,
+        ..Default::default()
+    };
+    if let Err(e) = crate::services::compactor::apply_gateway_compaction(
+        &state,
+        &provider_config,
+        &mut payload,
+        &tenant,
+    )
+    .await
     {
         tracing::warn!(error = %e, "Background gateway compaction failed; continuing with original payload");
     }
@@ -317,6 +337,7 @@ pub async fn execute_persisted_response(
         model_name.clone(),
         payload.clone(),
         None,
+        &tenant,
     )
     .await
     .map_err(|e| BackgroundExecuteError::Execution(format!("{e:?}")))?;
@@ -469,6 +490,20 @@ pub async fn execute_persisted_response(
         tool_results_count: None,
         tool_runtime_seconds: None,
         tool_exit_code: None,
+        jwt_subject: None,
+        error_code: None,
+        structured_output_repairs: None,
+        context_original_tokens: None,
+        context_compressed_tokens: None,
+        degraded_from_model: None,
+        degradation_reason: None,
+        ttft_ms: None,
+        inter_token_latency_ms: None,
+        output_tokens_per_second: None,
+        idempotency_key: None,
+        provider_attempts: None,
+        suspected_duplicate_attempts: None,
+        tags: None,
     };
 
     let provider_name_clone = provider_name.clone();
@@ -482,6 +517,7 @@ pub async fn execute_persisted_response(
         principal,
         mounted_skills,
         staged_input_files,
+        org_http_tools,
         Some(containers_owner),
         container_id_hint,
         resolved_shell_env,
@@ -509,6 +545,7 @@ pub async fn execute_persisted_response(
                 .and_then(|v| v.as_str())
                 .map(str::to_owned),
         }),
+        None,
     );
 
     // Wrap the response with the usage-tracking stream so token deltas
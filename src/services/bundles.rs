@@ -0,0 +1,1180 @@
+//! Export and import of organizational structure as a [`Bundle`].
+//!
+//! Import matches entities by slug, name or external ID, creating the ones
+//! that are missing and updating the ones that differ. Nothing absent from
+//! the bundle is deleted. The whole bundle is validated before anything is
+//! written, and importing the same bundle twice changes nothing the second
+//! time.
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    future::Future,
+    sync::Arc,
+};
+
+use chrono::Utc;
+use serde::Serialize;
+use thiserror::Error;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    authz::{AuthzEngine, PolicyRegistry},
+    db::{DbError, DbPool, DbResult, ListParams, ListResult},
+    models::{
+        AddTeamMember, ApiKey, BUNDLE_VERSION, Bundle, BundleApiKey, BundleApiKeyOwner,
+        BundleMember, BundleModelPricing, BundleOrganization, BundleProject, BundleRbacPolicy,
+        BundleTeam, BundleUser, CreateModelPricing, CreateOrgRbacPolicy, CreateOrganization,
+        CreateProject, CreateTeam, CreateUser, ImportAction, ImportChange, ImportReport,
+        MembershipSource, OrgRbacPolicy, Organization, PricingOwner, PricingSource, Project,
+        UpdateOrgRbacPolicy, UpdateOrganization, UpdateProject, UpdateTeam, UpdateTeamMember,
+        UpdateUser, User,
+    },
+};
+
+/// Page size used when walking lists during export.
+const PAGE_SIZE: i64 = 500;
+
+/// Reason recorded in the version history of imported RBAC policies.
+const IMPORT_REASON: &str = "Imported from bundle";
+
+/// Errors that can occur while exporting or importing a bundle.
+#[derive(Debug, Error)]
+pub enum BundleError {
+    #[error("Database error: {0}")]
+    Database(#[from] DbError),
+
+    #[error("Organization '{0}' not found")]
+    OrganizationNotFound(String),
+
+    #[error("Invalid bundle: {}", .0.join("; "))]
+    Invalid(Vec<String>),
+}
+
+/// Service layer for bundle export and import.
+#[derive(Clone)]
+pub struct BundleService {
+    db: Arc<DbPool>,
+    max_expression_length: usize,
+}
+
+impl BundleService {
+    pub fn new(db: Arc<DbPool>, max_expression_length: usize) -> Self {
+        Self {
+            db,
+            max_expression_length,
+        }
+    }
+
+    /// Export the given organizations, or all of them, with global model
+    /// pricing.
+    pub async fn export(&self, org_slugs: Option<&[String]>) -> Result<Bundle, BundleError> {
+        let orgs = match org_slugs {
+            Some(slugs) => {
+                let mut orgs = Vec::with_capacity(slugs.len());
+                for slug in slugs {
+                    let org = self
+                        .db
+                        .organizations()
+                        .get_by_slug(slug)
+                        .await?
+                        .ok_or_else(|| BundleError::OrganizationNotFound(slug.clone()))?;
+                    orgs.push(org);
+                }
+                orgs
+            }
+            None => {
+                let repo = self.db.organizations();
+                collect_all(|params| repo.list(params)).await?
+            }
+        };
+
+        let mut users = BTreeMap::new();
+        let mut organizations = Vec::with_capacity(orgs.len());
+        for org in orgs {
+            organizations.push(self.export_org(org, &mut users).await?);
+        }
+
+        let pricing_repo = self.db.model_pricing();
+        let model_pricing = collect_all(|params| pricing_repo.list_global(params))
+            .await?
+            .iter()
+            .map(|p| BundleModelPricing::from_db(p, None))
+            .collect();
+
+        Ok(Bundle {
+            version: BUNDLE_VERSION,
+            exported_at: Some(Utc::now()),
+            users: users.into_values().collect(),
+            organizations,
+            model_pricing,
+        })
+    }
+
+    async fn export_org(
+        &self,
+        org: Organization,
+        users: &mut BTreeMap<String, BundleUser>,
+    ) -> DbResult<BundleOrganization> {
+        let user_repo = self.db.users();
+        let team_repo = self.db.teams();
+        let project_repo = self.db.projects();
+        let key_repo = self.db.api_keys();
+        let pricing_repo = self.db.model_pricing();
+
+        let mut members = Vec::new();
+        for user in collect_all(|params| user_repo.list_org_members(org.id, params)).await? {
+            let role = user_repo
+                .get_org_memberships_for_user(user.id)
+                .await?
+                .into_iter()
+                .find(|m| m.org_id == org.id)
+                .map(|m| m.role);
+            if let Some(role) = role {
+                members.push(member(&user, role, users));
+            }
+        }
+
+        let mut api_keys = Vec::new();
+        api_keys.extend(
+            collect_all(|params| key_repo.list_by_org(org.id, params))
+                .await?
+                .into_iter()
+                .filter_map(|key| export_key(key, BundleApiKeyOwner::Organization)),
+        );
+
+        let mut teams = Vec::new();
+        let mut team_slugs = HashMap::new();
+        for team in collect_all(|params| team_repo.list_by_org(org.id, params)).await? {
+            team_slugs.insert(team.id, team.slug.clone());
+            let members = collect_all(|params| team_repo.list_members(team.id, params))
+                .await?
+                .into_iter()
+                .map(|m| {
+                    users
+                        .entry(m.external_id.clone())
+                        .or_insert_with(|| BundleUser {
+                            external_id: m.external_id.clone(),
+                            email: m.email,
+                            name: m.name,
+                        });
+                    BundleMember {
+                        user: m.external_id,
+                        role: m.role,
+                    }
+                })
+                .collect();
+            api_keys.extend(
+                collect_all(|params| key_repo.list_by_team(team.id, params))
+                    .await?
+                    .into_iter()
+                    .filter_map(|key| {
+                        export_key(
+                            key,
+                            BundleApiKeyOwner::Team {
+                                team: team.slug.clone(),
+                            },
+                        )
+                    }),
+            );
+            teams.push(BundleTeam {
+                slug: team.slug,
+                name: team.name,
+                members,
+            });
+        }
+
+        let mut model_pricing: Vec<_> =
+            collect_all(|params| pricing_repo.list_by_org(org.id, params))
+                .await?
+                .iter()
+                .map(|p| BundleModelPricing::from_db(p, None))
+                .collect();
+
+        let mut projects = Vec::new();
+        for project in collect_all(|params| project_repo.list_by_org(org.id, params)).await? {
+            let mut members = Vec::new();
+            for user in
+                collect_all(|params| user_repo.list_project_members(project.id, params)).await?
+            {
+                let role = user_repo
+                    .get_project_memberships_for_user(user.id)
+                    .await?
+                    .into_iter()
+                    .find(|m| m.project_id == project.id)
+                    .map(|m| m.role);
+                if let Some(role) = role {
+                    members.push(member(&user, role, users));
+                }
+            }
+            api_keys.extend(
+                collect_all(|params| key_repo.list_by_project(project.id, params))
+                    .await?
+                    .into_iter()
+                    .filter_map(|key| {
+                        export_key(
+                            key,
+                            BundleApiKeyOwner::Project {
+                                project: project.slug.clone(),
+                            },
+                        )
+                    }),
+            );
+            model_pricing.extend(
+                collect_all(|params| pricing_repo.list_by_project(project.id, params))
+                    .await?
+                    .iter()
+                    .map(|p| BundleModelPricing::from_db(p, Some(project.slug.clone()))),
+            );
+            projects.push(BundleProject {
+                team: project.team_id.and_then(|id| team_slugs.get(&id).cloned()),
+                slug: project.slug,
+                name: project.name,
+                request_defaults: project.request_defaults,
+                parameter_governance: project.parameter_governance,
+                model_access: project.model_access,
+                members,
+            });
+        }
+
+        let rbac_policies = self
+            .db
+            .org_rbac_policies()
+            .list_by_org(org.id)
+            .await?
+            .into_iter()
+            .map(|p| BundleRbacPolicy {
+                name: p.name,
+                description: p.description,
+                resource: p.resource,
+                action: p.action,
+                condition: p.condition,
+                effect: p.effect,
+                priority: p.priority,
+                enabled: p.enabled,
+            })
+            .collect();
+
+        Ok(BundleOrganization {
+            slug: org.slug,
+            name: org.name,
+            data_residency: org.data_residency,
+            request_defaults: org.request_defaults,
+            conversation_summaries: org.conversation_summaries,
+            network_policy: org.network_policy,
+            parameter_governance: org.parameter_governance,
+            model_access: org.model_access,
+            model_degradation: org.model_degradation,
+            members,
+            teams,
+            projects,
+            rbac_policies,
+            api_keys,
+            model_pricing,
+        })
+    }
+
+    /// Import a bundle. With `dry_run`, the bundle is validated and the
+    /// changes it would make are reported without writing anything.
+    pub async fn import(
+        &self,
+        bundle: Bundle,
+        dry_run: bool,
+        actor: Option<Uuid>,
+        registry: Option<&PolicyRegistry>,
+    ) -> Result<ImportReport, BundleError> {
+        self.validate(&bundle).await?;
+
+        let mut import = Import {
+            db: &self.db,
+            dry_run,
+            actor,
+            users: HashMap::new(),
+            changes: Vec::new(),
+        };
+        for user in &bundle.users {
+            import.user(user).await?;
+        }
+        for org in &bundle.organizations {
+            let org_id = import.organization(org).await?;
+            if !dry_run
+                && let (Some(org_id), Some(registry)) = (org_id, registry)
+                && !org.rbac_policies.is_empty()
+            {
+                let policies = self
+                    .db
+                    .org_rbac_policies()
+                    .list_enabled_by_org(org_id)
+                    .await?;
+                if let Err(e) = registry.refresh_org_policies(org_id, policies).await {
+                    tracing::warn!(org = %org.slug, error = %e, "Failed to refresh RBAC policies after import");
+                }
+            }
+        }
+        for pricing in &bundle.model_pricing {
+            import
+                .pricing(PricingOwner::Global, "global", pricing)
+                .await?;
+        }
+
+        Ok(ImportReport {
+            dry_run,
+            changes: import.changes,
+        })
+    }
+
+    /// Check the whole bundle before anything is written.
+    async fn validate(&self, bundle: &Bundle) -> Result<(), BundleError> {
+        let mut errors = Vec::new();
+        if bundle.version != BUNDLE_VERSION {
+            errors.push(format!(
+                "unsupported bundle version {} (expected {BUNDLE_VERSION})",
+                bundle.version
+            ));
+            return Err(BundleError::Invalid(errors));
+        }
+
+        let mut known_users = HashSet::new();
+        for user in &bundle.users {
+            let input = CreateUser {
+                external_id: user.external_id.clone(),
+                email: user.email.clone(),
+                name: user.name.clone(),
+            };
+            if let Err(e) = input.validate() {
+                errors.push(format!("user '{}': {e}", user.external_id));
+            }
+            if !known_users.insert(user.external_id.as_str()) {
+                errors.push(format!("user '{}' is listed twice", user.external_id));
+            }
+        }
+        // Members may also refer to users that already exist in this database
+        let mut missing_users = HashSet::new();
+        let members = bundle.organizations.iter().flat_map(|org| {
+            org.members
+                .iter()
+                .chain(org.teams.iter().flat_map(|t| &t.members))
+                .chain(org.projects.iter().flat_map(|p| &p.members))
+        });
+        for member in members {
+            if !known_users.contains(member.user.as_str())
+                && !missing_users.contains(member.user.as_str())
+                && self
+                    .db
+                    .users()
+                    .get_by_external_id(&member.user)
+                    .await?
+                    .is_none()
+            {
+                missing_users.insert(member.user.as_str());
+                errors.push(format!(
+                    "user '{}' is neither in the bundle nor in the database",
+                    member.user
+                ));
+            }
+            if member.role.is_empty() || member.role.len() > 64 {
+                errors.push(format!(
+                    "user '{}' has an invalid role '{}'",
+                    member.user, member.role
+                ));
+            }
+        }
+
+        let mut org_slugs = HashSet::new();
+        for org in &bundle.organizations {
+            if !org_slugs.insert(org.slug.as_str()) {
+                errors.push(format!("organization '{}' is listed twice", org.slug));
+            }
+            self.validate_org(org, &mut errors);
+        }
+        check_pricing(
+            &bundle.model_pricing,
+            "global",
+            &HashSet::new(),
+            &mut errors,
+        );
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(BundleError::Invalid(errors))
+        }
+    }
+
+    fn validate_org(&self, org: &BundleOrganization, errors: &mut Vec<String>) {
+        let input = CreateOrganization {
+            slug: org.slug.clone(),
+            name: org.name.clone(),
+        };
+        if let Err(e) = input.validate() {
+            errors.push(format!("organization '{}': {e}", org.slug));
+        }
+        let policies = [
+            org.network_policy.as_ref().map(|p| p.validate()),
+            org.parameter_governance.as_ref().map(|g| g.validate()),
+            org.model_access.as_ref().map(|p| p.validate()),
+            org.model_degradation.as_ref().map(|p| p.validate()),
+        ];
+        for error in policies.into_iter().flatten().filter_map(Result::err) {
+            errors.push(format!("organization '{}': {error}", org.slug));
+        }
+
+        let mut teams = HashSet::new();
+        for team in &org.teams {
+            let input = CreateTeam {
+                slug: team.slug.clone(),
+                name: team.name.clone(),
+            };
+            if let Err(e) = input.validate() {
+                errors.push(format!("team '{}/{}': {e}", org.slug, team.slug));
+            }
+            if !teams.insert(team.slug.as_str()) {
+                errors.push(format!("team '{}/{}' is listed twice", org.slug, team.slug));
+            }
+        }
+
+        let mut projects = HashSet::new();
+        for project in &org.projects {
+            let input = CreateProject {
+                slug: project.slug.clone(),
+                name: project.name.clone(),
+                team_id: None,
+            };
+            if let Err(e) = input.validate() {
+                errors.push(format!("project '{}/{}': {e}", org.slug, project.slug));
+            }
+            let policies = [
+                project.parameter_governance.as_ref().map(|g| g.validate()),
+                project.model_access.as_ref().map(|p| p.validate()),
+            ];
+            for error in policies.into_iter().flatten().filter_map(Result::err) {
+                errors.push(format!("project '{}/{}': {error}", org.slug, project.slug));
+            }
+            if !projects.insert(project.slug.as_str()) {
+                errors.push(format!(
+                    "project '{}/{}' is listed twice",
+                    org.slug, project.slug
+                ));
+            }
+            if let Some(team) = &project.team
+                && !teams.contains(team.as_str())
+            {
+                errors.push(format!(
+                    "project '{}/{}' refers to team '{team}', which is not in the bundle",
+                    org.slug, project.slug
+                ));
+            }
+        }
+
+        let mut policies = HashSet::new();
+        for policy in &org.rbac_policies {
+            let input = CreateOrgRbacPolicy {
+                name: policy.name.clone(),
+                description: policy.description.clone(),
+                resource: policy.resource.clone(),
+                action: policy.action.clone(),
+                condition: policy.condition.clone(),
+                effect: policy.effect,
+                priority: policy.priority,
+                enabled: policy.enabled,
+                reason: None,
+            };
+            if let Err(e) = input.validate() {
+                errors.push(format!("RBAC policy '{}/{}': {e}", org.slug, policy.name));
+            }
+            if let Err(e) = AuthzEngine::validate_expression_with_max_length(
+                &policy.condition,
+                self.max_expression_length,
+            ) {
+                errors.push(format!("RBAC policy '{}/{}': {e}", org.slug, policy.name));
+            }
+            if !policies.insert(policy.name.as_str()) {
+                errors.push(format!(
+                    "RBAC policy '{}/{}' is listed twice",
+                    org.slug, policy.name
+                ));
+            }
+        }
+
+        check_pricing(&org.model_pricing, &org.slug, &projects, errors);
+    }
+}
+
+/// Walk every page of a list.
+async fn collect_all<T, F, Fut>(mut fetch: F) -> DbResult<Vec<T>>
+where
+    F: FnMut(ListParams) -> Fut,
+    Fut: Future<Output = DbResult<ListResult<T>>>,
+{
+    let mut params = ListParams {
+        limit: Some(PAGE_SIZE),
+        ..Default::default()
+    };
+    let mut items = Vec::new();
+    loop {
+        let page = fetch(params.clone()).await?;
+        items.extend(page.items);
+        match page.cursors.next {
+            Some(next) if page.has_more => params.cursor = Some(next),
+            _ => break,
+        }
+    }
+    Ok(items)
+}
+
+/// Record a membership, adding the user to the bundle's users.
+fn member(user: &User, role: String, users: &mut BTreeMap<String, BundleUser>) -> BundleMember {
+    users
+        .entry(user.external_id.clone())
+        .or_insert_with(|| BundleUser {
+            external_id: user.external_id.clone(),
+            email: user.email.clone(),
+            name: user.name.clone(),
+        });
+    BundleMember {
+        user: user.external_id.clone(),
+        role,
+    }
+}
+
+/// Metadata of an active key. Revoked keys aren't exported.
+fn export_key(key: ApiKey, owner: BundleApiKeyOwner) -> Option<BundleApiKey> {
+    if key.revoked_at.is_some() {
+        return None;
+    }
+    Some(BundleApiKey {
+        name: key.name,
+        key_prefix: key.key_prefix,
+        owner,
+        scopes: key.scopes,
+        allowed_models: key.allowed_models,
+        ip_allowlist: key.ip_allowlist,
+        budget_limit_cents: key.budget_limit_cents,
+        budget_period: key.budget_period,
+        rate_limit_rpm: key.rate_limit_rpm,
+        rate_limit_tpm: key.rate_limit_tpm,
+        expires_at: key.expires_at,
+    })
+}
+
+fn check_pricing(
+    pricing: &[BundleModelPricing],
+    scope: &str,
+    projects: &HashSet<&str>,
+    errors: &mut Vec<String>,
+) {
+    let mut seen = HashSet::new();
+    for entry in pricing {
+        let key = format!("{scope}/{}/{}", entry.provider, entry.model);
+        if entry.provider.is_empty()
+            || entry.provider.len() > 64
+            || entry.model.is_empty()
+            || entry.model.len() > 128
+        {
+            errors.push(format!("model pricing '{key}': invalid provider or model"));
+        }
+        if let Some(project) = &entry.project
+            && !projects.contains(project.as_str())
+        {
+            errors.push(format!(
+                "model pricing '{key}' refers to project '{project}', which is not in the bundle"
+            ));
+        }
+        if !seen.insert((entry.project.as_deref(), &entry.provider, &entry.model)) {
+            errors.push(format!("model pricing '{key}' is listed twice"));
+        }
+    }
+}
+
+/// Whether two values serialize the same. Used to compare policies that
+/// don't implement `PartialEq`.
+fn same<T: Serialize>(a: &T, b: &T) -> bool {
+    serde_json::to_value(a).ok() == serde_json::to_value(b).ok()
+}
+
+/// State of a single import run. IDs are `None` for entities that don't
+/// exist yet during a dry run.
+struct Import<'a> {
+    db: &'a DbPool,
+    dry_run: bool,
+    actor: Option<Uuid>,
+    /// User IDs by external ID
+    users: HashMap<String, Option<Uuid>>,
+    changes: Vec<ImportChange>,
+}
+
+impl Import<'_> {
+    fn record(&mut self, resource: &str, key: String, action: ImportAction) {
+        self.changes.push(ImportChange {
+            resource: resource.to_string(),
+            key,
+            action,
+            note: None,
+        });
+    }
+
+    fn skip(&mut self, resource: &str, key: String, note: &str) {
+        self.changes.push(ImportChange {
+            resource: resource.to_string(),
+            key,
+            action: ImportAction::Skip,
+            note: Some(note.to_string()),
+        });
+    }
+
+    async fn user(&mut self, user: &BundleUser) -> DbResult<()> {
+        let repo = self.db.users();
+        let key = user.external_id.clone();
+        let id = match repo.get_by_external_id(&user.external_id).await? {
+            None => {
+                self.record("user", key, ImportAction::Create);
+                if self.dry_run {
+                    None
+                } else {
+                    let input = CreateUser {
+                        external_id: user.external_id.clone(),
+                        email: user.email.clone(),
+                        name: user.name.clone(),
+                    };
+                    Some(repo.create(input).await?.id)
+                }
+            }
+            Some(existing) => {
+                // Only fill in or change fields the bundle sets
+                let email = user
+                    .email
+                    .clone()
+                    .filter(|e| existing.email.as_ref() != Some(e));
+                let name = user
+                    .name
+                    .clone()
+                    .filter(|n| existing.name.as_ref() != Some(n));
+                if email.is_none() && name.is_none() {
+                    self.record("user", key, ImportAction::Unchanged);
+                } else {
+                    self.record("user", key, ImportAction::Update);
+                    if !self.dry_run {
+                        repo.update(existing.id, UpdateUser { email, name }).await?;
+                    }
+                }
+                Some(existing.id)
+            }
+        };
+        self.users.insert(user.external_id.clone(), id);
+        Ok(())
+    }
+
+    /// ID of a member's user, looking up users that aren't in the bundle.
+    async fn user_id(&mut self, external_id: &str) -> DbResult<Option<Uuid>> {
+        if let Some(id) = self.users.get(external_id) {
+            return Ok(*id);
+        }
+        let id = self
+            .db
+            .users()
+            .get_by_external_id(external_id)
+            .await?
+            .map(|u| u.id);
+        self.users.insert(external_id.to_string(), id);
+        Ok(id)
+    }
+
+    /// Import an organization and everything in it, returning its ID.
+    async fn organization(&mut self, org: &BundleOrganization) -> DbResult<Option<Uuid>> {
+        let repo = self.db.organizations();
+        let existing = repo.get_by_slug(&org.slug).await?;
+        let org_id = match &existing {
+            None => {
+                self.record("organization", org.slug.clone(), ImportAction::Create);
+                if self.dry_run {
+                    None
+                } else {
+                    let created = repo
+                        .create(CreateOrganization {
+                            slug: org.slug.clone(),
+                            name: org.name.clone(),
+                        })
+                        .await?;
+                    self.apply_org_settings(&created, org).await?;
+                    Some(created.id)
+                }
+            }
+            Some(existing) => {
+                if org_matches(existing, org) {
+                    self.record("organization", org.slug.clone(), ImportAction::Unchanged);
+                } else {
+                    self.record("organization", org.slug.clone(), ImportAction::Update);
+                    if !self.dry_run {
+                        self.apply_org_settings(existing, org).await?;
+                    }
+                }
+                Some(existing.id)
+            }
+        };
+
+        for member in &org.members {
+            self.org_member(org_id, &org.slug, member).await?;
+        }
+
+        let mut team_ids = HashMap::new();
+        for team in &org.teams {
+            let team_id = self.team(org_id, &org.slug, team).await?;
+            team_ids.insert(team.slug.as_str(), team_id);
+        }
+
+        let mut project_ids = HashMap::new();
+        for project in &org.projects {
+            let team_id = project
+                .team
+                .as_deref()
+                .and_then(|slug| team_ids.get(slug).copied().flatten());
+            let project_id = self.project(org_id, &org.slug, project, team_id).await?;
+            project_ids.insert(project.slug.as_str(), project_id);
+        }
+
+        for policy in &org.rbac_policies {
+            self.rbac_policy(org_id, &org.slug, policy).await?;
+        }
+
+        for key in &org.api_keys {
+            self.skip(
+                "api_key",
+                format!("{}/{}", org.slug, key.name),
+                "API keys are exported without their secret and must be issued again",
+            );
+        }
+
+        for pricing in &org.model_pricing {
+            match &pricing.project {
+                Some(slug) => {
+                    let scope = format!("{}/{slug}", org.slug);
+                    match project_ids.get(slug.as_str()).copied().flatten() {
+                        Some(project_id) => {
+                            self.pricing(PricingOwner::Project { project_id }, &scope, pricing)
+                                .await?
+                        }
+                        None => self.planned_pricing(&scope, pricing),
+                    }
+                }
+                None => match org_id {
+                    Some(org_id) => {
+                        self.pricing(PricingOwner::Organization { org_id }, &org.slug, pricing)
+                            .await?
+                    }
+                    None => self.planned_pricing(&org.slug, pricing),
+                },
+            }
+        }
+
+        Ok(org_id)
+    }
+
+    async fn apply_org_settings(
+        &self,
+        existing: &Organization,
+        org: &BundleOrganization,
+    ) -> DbResult<()> {
+        let repo = self.db.organizations();
+        repo.update(
+            existing.id,
+            UpdateOrganization {
+                name: Some(org.name.clone()),
+                data_residency: Some(org.data_residency.clone()),
+                request_defaults: Some(org.request_defaults.clone()),
+                conversation_summaries: Some(org.conversation_summaries.clone()),
+            },
+        )
+        .await?;
+        if !same(&existing.network_policy, &org.network_policy) {
+            repo.set_network_policy(existing.id, org.network_policy.as_ref())
+                .await?;
+        }
+        if !same(&existing.parameter_governance, &org.parameter_governance) {
+            repo.set_parameter_governance(existing.id, org.parameter_governance.as_ref())
+                .await?;
+        }
+        if !same(&existing.model_access, &org.model_access) {
+            repo.set_model_access(existing.id, org.model_access.as_ref())
+                .await?;
+        }
+        if !same(&existing.model_degradation, &org.model_degradation) {
+            repo.set_model_degradation(existing.id, org.model_degradation.as_ref())
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn org_member(
+        &mut self,
+        org_id: Option<Uuid>,
+        org_slug: &str,
+        member: &BundleMember,
+    ) -> DbResult<()> {
+        let key = format!("{org_slug}/{}", member.user);
+        let (Some(org_id), Some(user_id)) = (org_id, self.user_id(&member.user).await?) else {
+            self.record("organization_member", key, ImportAction::Create);
+            return Ok(());
+        };
+        let repo = self.db.users();
+        let memberships = repo.get_org_memberships_for_user(user_id).await?;
+        match memberships.iter().find(|m| m.org_id == org_id) {
+            Some(m) if m.role == member.role => {
+                self.record("organization_member", key, ImportAction::Unchanged)
+            }
+            Some(_) => {
+                self.record("organization_member", key, ImportAction::Update);
+                if !self.dry_run {
+                    repo.update_org_member_role(user_id, org_id, &member.role)
+                        .await?;
+                }
+            }
+            // Users belong to at most one organization
+            None if !memberships.is_empty() => self.skip(
+                "organization_member",
+                key,
+                "user already belongs to another organization",
+            ),
+            None => {
+                self.record("organization_member", key, ImportAction::Create);
+                if !self.dry_run {
+                    repo.add_to_org(user_id, org_id, &member.role, MembershipSource::Manual)
+                        .await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn team(
+        &mut self,
+        org_id: Option<Uuid>,
+        org_slug: &str,
+        team: &BundleTeam,
+    ) -> DbResult<Option<Uuid>> {
+        let repo = self.db.teams();
+        let key = format!("{org_slug}/{}", team.slug);
+        let existing = match org_id {
+            Some(org_id) => repo.get_by_slug(org_id, &team.slug).await?,
+            None => None,
+        };
+        let team_id = match existing {
+            None => {
+                self.record("team", key.clone(), ImportAction::Create);
+                match org_id {
+                    Some(org_id) if !self.dry_run => {
+                        let input = CreateTeam {
+                            slug: team.slug.clone(),
+                            name: team.name.clone(),
+                        };
+                        Some(repo.create(org_id, input).await?.id)
+                    }
+                    _ => None,
+                }
+            }
+            Some(existing) if existing.name == team.name => {
+                self.record("team", key.clone(), ImportAction::Unchanged);
+                Some(existing.id)
+            }
+            Some(existing) => {
+                self.record("team", key.clone(), ImportAction::Update);
+                if !self.dry_run {
+                    let input = UpdateTeam {
+                        name: Some(team.name.clone()),
+                    };
+                    repo.update(existing.id, input).await?;
+                }
+                Some(existing.id)
+            }
+        };
+
+        for member in &team.members {
+            let member_key = format!("{key}/{}", member.user);
+            let (Some(team_id), Some(user_id)) = (team_id, self.user_id(&member.user).await?)
+            else {
+                self.record("team_member", member_key, ImportAction::Create);
+                continue;
+            };
+            match repo.get_member(team_id, user_id).await? {
+                Some(m) if m.role == member.role => {
+                    self.record("team_member", member_key, ImportAction::Unchanged)
+                }
+                Some(_) => {
+                    self.record("team_member", member_key, ImportAction::Update);
+                    if !self.dry_run {
+                        let input = UpdateTeamMember {
+                            role: member.role.clone(),
+                        };
+                        repo.update_member_role(team_id, user_id, input).await?;
+                    }
+                }
+                None => {
+                    self.record("team_member", member_key, ImportAction::Create);
+                    if !self.dry_run {
+                        let input = AddTeamMember {
+                            user_id,
+                            role: member.role.clone(),
+                            source: MembershipSource::Manual,
+                        };
+                        repo.add_member(team_id, input).await?;
+                    }
+                }
+            }
+        }
+        Ok(team_id)
+    }
+
+    async fn project(
+        &mut self,
+        org_id: Option<Uuid>,
+        org_slug: &str,
+        project: &BundleProject,
+        team_id: Option<Uuid>,
+    ) -> DbResult<Option<Uuid>> {
+        let repo = self.db.projects();
+        let key = format!("{org_slug}/{}", project.slug);
+        let existing = match org_id {
+            Some(org_id) => repo.get_by_slug(org_id, &project.slug).await?,
+            None => None,
+        };
+        let project_id = match existing {
+            None => {
+                self.record("project", key.clone(), ImportAction::Create);
+                match org_id {
+                    Some(org_id) if !self.dry_run => {
+                        let input = CreateProject {
+                            slug: project.slug.clone(),
+                            name: project.name.clone(),
+                            team_id,
+                        };
+                        let created = repo.create(org_id, input).await?;
+                        self.apply_project_settings(&created, project, team_id)
+                            .await?;
+                        Some(created.id)
+                    }
+                    _ => None,
+                }
+            }
+            Some(existing) => {
+                if project_matches(&existing, project, team_id) {
+                    self.record("project", key.clone(), ImportAction::Unchanged);
+                } else {
+                    self.record("project", key.clone(), ImportAction::Update);
+                    if !self.dry_run {
+                        self.apply_project_settings(&existing, project, team_id)
+                            .await?;
+                    }
+                }
+                Some(existing.id)
+            }
+        };
+
+        let user_repo = self.db.users();
+        for member in &project.members {
+            let member_key = format!("{key}/{}", member.user);
+            let (Some(project_id), Some(user_id)) = (project_id, self.user_id(&member.user).await?)
+            else {
+                self.record("project_member", member_key, ImportAction::Create);
+                continue;
+            };
+            let current = user_repo
+                .get_project_memberships_for_user(user_id)
+                .await?
+                .into_iter()
+                .find(|m| m.project_id == project_id);
+            match current {
+                Some(m) if m.role == member.role => {
+                    self.record("project_member", member_key, ImportAction::Unchanged)
+                }
+                Some(_) => {
+                    self.record("project_member", member_key, ImportAction::Update);
+                    if !self.dry_run {
+                        user_repo
+                            .update_project_member_role(user_id, project_id, &member.role)
+                            .await?;
+                    }
+                }
+                None => {
+                    self.record("project_member", member_key, ImportAction::Create);
+                    if !self.dry_run {
+                        user_repo
+                            .add_to_project(
+                                user_id,
+                                project_id,
+                                &member.role,
+                                MembershipSource::Manual,
+                            )
+                            .await?;
+                    }
+                }
+            }
+        }
+        Ok(project_id)
+    }
+
+    async fn apply_project_settings(
+        &self,
+        existing: &Project,
+        project: &BundleProject,
+        team_id: Option<Uuid>,
+    ) -> DbResult<()> {
+        let repo = self.db.projects();
+        repo.update(
+            existing.id,
+            UpdateProject {
+                name: Some(project.name.clone()),
+                team_id: Some(team_id),
+                request_defaults: Some(project.request_defaults.clone()),
+            },
+        )
+        .await?;
+        if !same(
+            &existing.parameter_governance,
+            &project.parameter_governance,
+        ) {
+            repo.set_parameter_governance(existing.id, project.parameter_governance.as_ref())
+                .await?;
+        }
+        if !same(&existing.model_access, &project.model_access) {
+            repo.set_model_access(existing.id, project.model_access.as_ref())
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn rbac_policy(
+        &mut self,
+        org_id: Option<Uuid>,
+        org_slug: &str,
+        policy: &BundleRbacPolicy,
+    ) -> DbResult<()> {
+        let repo = self.db.org_rbac_policies();
+        let key = format!("{org_slug}/{}", policy.name);
+        let Some(org_id) = org_id else {
+            self.record("rbac_policy", key, ImportAction::Create);
+            return Ok(());
+        };
+        match repo.get_by_org_and_name(org_id, &policy.name).await? {
+            None => {
+                self.record("rbac_policy", key, ImportAction::Create);
+                if !self.dry_run {
+                    let input = CreateOrgRbacPolicy {
+                        name: policy.name.clone(),
+                        description: policy.description.clone(),
+                        resource: policy.resource.clone(),
+                        action: policy.action.clone(),
+                        condition: policy.condition.clone(),
+                        effect: policy.effect,
+                        priority: policy.priority,
+                        enabled: policy.enabled,
+                        reason: Some(IMPORT_REASON.to_string()),
+                    };
+                    repo.create(org_id, input, self.actor).await?;
+                }
+            }
+            Some(existing) if rbac_policy_matches(&existing, policy) => {
+                self.record("rbac_policy", key, ImportAction::Unchanged)
+            }
+            Some(existing) => {
+                self.record("rbac_policy", key, ImportAction::Update);
+                if !self.dry_run {
+                    let input = UpdateOrgRbacPolicy {
+                        name: None,
+                        description: Some(policy.description.clone()),
+                        resource: Some(policy.resource.clone()),
+                        action: Some(policy.action.clone()),
+                        condition: Some(policy.condition.clone()),
+                        effect: Some(policy.effect),
+                        priority: Some(policy.priority),
+                        enabled: Some(policy.enabled),
+                        reason: Some(IMPORT_REASON.to_string()),
+                    };
+                    repo.update(existing.id, input, self.actor).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Record pricing whose owner doesn't exist yet during a dry run.
+    fn planned_pricing(&mut self, scope: &str, pricing: &BundleModelPricing) {
+        let key = format!("{scope}/{}/{}", pricing.provider, pricing.model);
+        self.record("model_pricing", key, ImportAction::Create);
+    }
+
+    async fn pricing(
+        &mut self,
+        owner: PricingOwner,
+        scope: &str,
+        pricing: &BundleModelPricing,
+    ) -> DbResult<()> {
+        let repo = self.db.model_pricing();
+        let key = format!("{scope}/{}/{}", pricing.provider, pricing.model);
+        let action = match repo
+            .get_by_provider_model(&owner, &pricing.provider, &pricing.model)
+            .await?
+        {
+            None => ImportAction::Create,
+            Some(existing)
+                if BundleModelPricing::from_db(&existing, pricing.project.clone()) == *pricing =>
+            {
+                ImportAction::Unchanged
+            }
+            Some(_) => ImportAction::Update,
+        };
+        self.record("model_pricing", key, action);
+        if !self.dry_run && action != ImportAction::Unchanged {
+            repo.upsert(CreateModelPricing {
+                owner,
+                provider: pricing.provider.clone(),
+                model: pricing.model.clone(),
+                input_per_1m_tokens: pricing.input_per_1m_tokens,
+                output_per_1m_tokens: pricing.output_per_1m_tokens,
+                per_image: pricing.per_image,
+                per_request: pricing.per_request,
+                cached_input_per_1m_tokens: pricing.cached_input_per_1m_tokens,
+                cache_write_per_1m_tokens: pricing.cache_write_per_1m_tokens,
+                reasoning_per_1m_tokens: pricing.reasoning_per_1m_tokens,
+                per_second: pricing.per_second,
+                per_1m_characters: pricing.per_1m_characters,
+                source: PricingSource::Manual,
+            })
+            .await?;
+        }
+        Ok(())
+    }
+}
+
+fn org_matches(existing: &Organization, org: &BundleOrganization) -> bool {
+    existing.name == org.name
+        && same(&existing.data_residency, &org.data_residency)
+        && same(&existing.request_defaults, &org.request_defaults)
+        && same(
+            &existing.conversation_summaries,
+            &org.conversation_summaries,
+        )
+        && same(&existing.network_policy, &org.network_policy)
+        && same(&existing.parameter_governance, &org.parameter_governance)
+        && same(&existing.model_access, &org.model_access)
+        && same(&existing.model_degradation, &org.model_degradation)
+}
+
+fn project_matches(existing: &Project, project: &BundleProject, team_id: Option<Uuid>) -> bool {
+    existing.name == project.name
+        && existing.team_id == team_id
+        && same(&existing.request_defaults, &project.request_defaults)
+        && same(
+            &existing.parameter_governance,
+            &project.parameter_governance,
+        )
+        && same(&existing.model_access, &project.model_access)
+}
+
+fn rbac_policy_matches(existing: &OrgRbacPolicy, policy: &BundleRbacPolicy) -> bool {
+    existing.description == policy.description
+        && existing.resource == policy.resource
+        && existing.action == policy.action
+        && existing.condition == policy.condition
+        && existing.effect == policy.effect
+        && existing.priority == policy.priority
+        && existing.enabled == policy.enabled
+}
@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    db::{DbPool, DbResult, ListParams, repos::ListResult},
+    models::{CertMatchType, ClientCertMapping, CreateClientCertMapping},
+};
+
+/// Service layer for client certificate → service account mappings
+#[derive(Clone)]
+pub struct ClientCertMappingService {
+    db: Arc<DbPool>,
+}
+
+impl ClientCertMappingService {
+    pub fn new(db: Arc<DbPool>) -> Self {
+        Self { db }
+    }
+
+    /// Map a certificate identifier to a service account. The match value is
+    /// normalized before storage so lookups are insensitive to formatting.
+    pub async fn create(
+        &self,
+        org_id: Uuid,
+        service_account_id: Uuid,
+        mut input: CreateClientCertMapping,
+    ) -> DbResult<ClientCertMapping> {
+        input.match_value = input.match_type.normalize(&input.match_value);
+        self.db
+            .client_cert_mappings()
+            .create(org_id, service_account_id, input)
+            .await
+    }
+
+    /// Get mapping by ID
+    pub async fn get_by_id(&self, id: Uuid) -> DbResult<Option<ClientCertMapping>> {
+        self.db.client_cert_mappings().get_by_id(id).await
+    }
+
+    /// List mappings for a service account with pagination
+    pub async fn list_by_service_account(
+        &self,
+        service_account_id: Uuid,
+        params: ListParams,
+    ) -> DbResult<ListResult<ClientCertMapping>> {
+        self.db
+            .client_cert_mappings()
+            .list_by_service_account(service_account_id, params)
+            .await
+    }
+
+    /// Delete a mapping by ID
+    pub async fn delete(&self, id: Uuid) -> DbResult<()> {
+        self.db.client_cert_mappings().delete(id).await
+    }
+
+    /// Resolve the identifiers of a presented certificate to a single mapping.
+    ///
+    /// The most specific match type wins (see [`CertMatchType`]). If the
+    /// winning match type maps to more than one service account, e.g. a cert
+    /// carrying two DNS SANs mapped to different accounts, the certificate is
+    /// ambiguous and `None` is returned rather than picking one arbitrarily.
+    pub async fn resolve(
+        &self,
+        identifiers: &[(CertMatchType, String)],
+    ) -> DbResult<Option<ClientCertMapping>> {
+        if identifiers.is_empty() {
+            return Ok(None);
+        }
+        let mut mappings = self
+            .db
+            .client_cert_mappings()
+            .find_by_identifiers(identifiers)
+            .await?;
+        mappings.sort_by_key(|m| m.match_type);
+
+        let Some(best) = mappings.first() else {
+            return Ok(None);
+        };
+        let ambiguous = mappings.iter().any(|m| {
+            m.match_type == best.match_type && m.service_account_id != best.service_account_id
+        });
+        if ambiguous {
+            tracing::warn!(
+                match_type = best.match_type.as_str(),
+                "Client certificate matches multiple service accounts; ignoring"
+            );
+            return Ok(None);
+        }
+        Ok(mappings.into_iter().next())
+    }
+}
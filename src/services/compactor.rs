@@ -37,7 +37,8 @@ use crate::{
         },
     },
     config::{ProviderConfig, ResponsesCompactionStrategy},
-    routes::execution::ProviderExecutor,
+    providers::Tenant,
+    routes::execution::{ResponsesExecutor, execute_provider},
 };
 
 /// Errors raised by the compactor. Most are non-fatal — the caller
@@ -63,6 +64,7 @@ pub async fn apply_gateway_compaction(
     state: &AppState,
     provider_config: &ProviderConfig,
     payload: &mut CreateResponsesPayload,
+    tenant: &Tenant,
 ) -> Result<bool, CompactionError> {
     let compaction_cfg = &state.config.features.responses.compaction;
     if !compaction_cfg.enabled {
@@ -143,7 +145,8 @@ pub async fn apply_gateway_compaction(
             let prompt = prompt_override
                 .filter(|p| !p.trim().is_empty())
                 .unwrap_or_else(|| compaction_cfg.default_prompt.clone());
-            match llm_replacement(state, provider_config, payload, &dropped, &prompt).await {
+            match llm_replacement(state, provider_config, payload, &dropped, &prompt, tenant).await
+            {
                 Ok(text) => Some(make_summary_item(&text)),
                 Err(e) => {
                     warn!(
@@ -289,6 +292,7 @@ async fn llm_replacement(
     parent: &CreateResponsesPayload,
     dropped: &[ResponsesInputItem],
     prompt: &str,
+    tenant: &Tenant,
 ) -> Result<String, CompactionError> {
     // Render a concise transcript out of the dropped items so the
     // summariser has plain text to chew on (instead of forcing the
@@ -321,11 +325,12 @@ async fn llm_replacement(
         }),
     ]));
 
-    let response = crate::routes::execution::ResponsesExecutor::execute(
+    let response = execute_provider::<ResponsesExecutor>(
         state,
         provider_config_name(provider_config),
         provider_config,
         summary_payload,
+        tenant,
     )
     .await
     .map_err(|e| CompactionError::SummariseCall(format!("{e:?}")))?;
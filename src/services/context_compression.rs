@@ -0,0 +1,609 @@
+//! Context compression for long chat completion conversations.
+//!
+//! With `[features.context_compression]` enabled, a chat completion whose
+//! estimated prompt tokens exceed `threshold_tokens` has its older turns
+//! shortened before dispatch:
+//!
+//! - **`sliding_window`**: drop the oldest turns until the estimate falls
+//!   under the threshold.
+//! - **`summarize`**: replace every older turn with a summary from one call
+//!   to `summary_model` (or the request's own model). Falls back to
+//!   `sliding_window` if that call fails.
+//! - **`importance`**: drop the lowest-scoring turns first. A turn scores
+//!   higher the more of the latest user message's words it shares, the more
+//!   recent it is, and for user messages over assistant and tool messages.
+//!
+//! System and developer messages and the last `keep_recent_messages` turns
+//! are never touched, and an assistant message with tool calls is kept or
+//! dropped together with its tool results. Token counts are the same rough
+//! estimate as the Responses compactor (1 token ≈ 4 characters); images and
+//! audio don't count.
+
+use std::{collections::HashSet, time::Duration};
+
+use serde_json::Value;
+use tracing::{info, warn};
+
+use crate::{
+    AppState,
+    api_types::{
+        CreateChatCompletionPayload, Message, MessageContent, chat_completion::ContentPart,
+    },
+    config::{ContextCompressionConfig, ContextCompressionStrategy, ProviderConfig},
+    providers::Tenant,
+    routes::execution::{ChatCompletionExecutor, execute_provider},
+    routing::{resolver, route_model_extended},
+};
+
+/// Response header with the estimated prompt tokens as sent by the client.
+pub const ORIGINAL_TOKENS_HEADER: &str = "X-Context-Original-Tokens";
+
+/// Response header with the estimated prompt tokens after compression.
+pub const COMPRESSED_TOKENS_HEADER: &str = "X-Context-Compressed-Tokens";
+
+/// Max summary response body read from the provider.
+const MAX_SUMMARY_RESPONSE_BYTES: usize = 1024 * 1024;
+
+/// The request's resolved route, used to summarize when no `summary_model`
+/// is configured.
+pub struct RequestRoute<'a> {
+    pub provider_name: &'a str,
+    pub provider_config: &'a ProviderConfig,
+    pub model_name: &'a str,
+    pub tenant: &'a Tenant,
+}
+
+/// A compression applied to a request.
+pub struct Compression {
+    /// Strategy that produced the messages; `sliding_window` when a
+    /// summary was asked for but couldn't be made.
+    pub strategy: ContextCompressionStrategy,
+    pub original_tokens: u32,
+    pub compressed_tokens: u32,
+    /// Messages removed from the conversation
+    pub dropped_messages: usize,
+    /// The conversation as the client sent it
+    pub original_messages: Vec<Message>,
+}
+
+/// Compress `payload.messages` in place when they exceed the threshold.
+///
+/// `None` when compression is disabled, the request is under the threshold,
+/// or every turn is protected.
+pub async fn compress(
+    state: &AppState,
+    payload: &mut CreateChatCompletionPayload,
+    route: RequestRoute<'_>,
+) -> Option<Compression> {
+    let config = &state.config.features.context_compression;
+    if !config.enabled {
+        return None;
+    }
+    let original_tokens = estimate_tokens(&payload.messages);
+    if original_tokens <= config.threshold_tokens {
+        return None;
+    }
+    let units = compressible_units(&payload.messages, config.keep_recent_messages);
+    if units.is_empty() {
+        return None;
+    }
+
+    let ((messages, dropped_messages), strategy) = match config.strategy {
+        ContextCompressionStrategy::SlidingWindow => (
+            sliding_window(&payload.messages, &units, config.threshold_tokens),
+            ContextCompressionStrategy::SlidingWindow,
+        ),
+        ContextCompressionStrategy::Importance => (
+            by_importance(&payload.messages, &units, config.threshold_tokens),
+            ContextCompressionStrategy::Importance,
+        ),
+        ContextCompressionStrategy::Summarize => {
+            match summarize(state, config, &payload.messages, &units, &route).await {
+                Ok(summary) => (
+                    (
+                        replace_units(&payload.messages, &units, summary_message(&summary)),
+                        units.iter().map(Vec::len).sum(),
+                    ),
+                    ContextCompressionStrategy::Summarize,
+                ),
+                Err(e) => {
+                    warn!(
+                        error = %e,
+                        "Context summarization failed; falling back to sliding window"
+                    );
+                    (
+                        sliding_window(&payload.messages, &units, config.threshold_tokens),
+                        ContextCompressionStrategy::SlidingWindow,
+                    )
+                }
+            }
+        }
+    };
+
+    let compressed_tokens = estimate_tokens(&messages);
+    let original_messages = std::mem::replace(&mut payload.messages, messages);
+    info!(
+        strategy = strategy.as_str(),
+        original_tokens,
+        compressed_tokens,
+        dropped_messages,
+        threshold = config.threshold_tokens,
+        "Compressed chat completion context"
+    );
+    Some(Compression {
+        strategy,
+        original_tokens,
+        compressed_tokens,
+        dropped_messages,
+        original_messages,
+    })
+}
+
+/// Rough token estimate of a conversation (1 token ≈ 4 characters).
+pub fn estimate_tokens(messages: &[Message]) -> u32 {
+    let chars: usize = messages.iter().map(message_chars).sum();
+    chars.div_ceil(4) as u32
+}
+
+fn message_chars(message: &Message) -> usize {
+    match message {
+        Message::System { content, .. }
+        | Message::User { content, .. }
+        | Message::Developer { content, .. }
+        | Message::Tool { content, .. } => content_chars(content),
+        Message::Assistant {
+            content,
+            tool_calls,
+            refusal,
+            ..
+        } => {
+            content.as_ref().map(content_chars).unwrap_or(0)
+                + refusal.as_ref().map(String::len).unwrap_or(0)
+                + tool_calls
+                    .iter()
+                    .flatten()
+                    .map(|c| c.function.name.len() + c.function.arguments.len())
+                    .sum::<usize>()
+        }
+    }
+}
+
+fn content_chars(content: &MessageContent) -> usize {
+    match content {
+        MessageContent::Text(text) => text.len(),
+        MessageContent::Parts(parts) => parts
+            .iter()
+            .map(|part| match part {
+                ContentPart::Text { text, .. } => text.len(),
+                _ => 0,
+            })
+            .sum(),
+    }
+}
+
+fn content_text(content: &MessageContent) -> String {
+    match content {
+        MessageContent::Text(text) => text.clone(),
+        MessageContent::Parts(parts) => parts
+            .iter()
+            .filter_map(|part| match part {
+                ContentPart::Text { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join(" "),
+    }
+}
+
+fn is_instruction(message: &Message) -> bool {
+    matches!(message, Message::System { .. } | Message::Developer { .. })
+}
+
+/// Older turns that may be compressed, oldest first, as message indices.
+///
+/// An assistant message with tool calls and the tool results that follow it
+/// form one unit. System and developer messages and the most recent
+/// `keep_recent` messages are excluded; the recent window is widened so it
+/// never starts with a tool result.
+fn compressible_units(messages: &[Message], keep_recent: usize) -> Vec<Vec<usize>> {
+    let turns: Vec<usize> = (0..messages.len())
+        .filter(|&i| !is_instruction(&messages[i]))
+        .collect();
+    if turns.len() <= keep_recent {
+        return Vec::new();
+    }
+    let mut kept_from = turns.len() - keep_recent;
+    while kept_from > 0 && matches!(messages[turns[kept_from]], Message::Tool { .. }) {
+        kept_from -= 1;
+    }
+
+    let mut units: Vec<Vec<usize>> = Vec::new();
+    for &i in &turns[..kept_from] {
+        let continues_tool_call = matches!(messages[i], Message::Tool { .. })
+            && units.last().is_some_and(|unit| {
+                matches!(
+                    &messages[unit[0]],
+                    Message::Assistant { tool_calls: Some(calls), .. } if !calls.is_empty()
+                )
+            });
+        match units.last_mut() {
+            Some(unit) if continues_tool_call => unit.push(i),
+            _ => units.push(vec![i]),
+        }
+    }
+    units
+}
+
+fn unit_tokens(messages: &[Message], unit: &[usize]) -> u32 {
+    let chars: usize = unit.iter().map(|&i| message_chars(&messages[i])).sum();
+    chars.div_ceil(4) as u32
+}
+
+/// Drop units in `order` until the estimate is at most `threshold`, then
+/// note how many messages were omitted. Returns the messages and how many
+/// were dropped.
+fn drop_until_under(
+    messages: &[Message],
+    units: &[Vec<usize>],
+    order: impl IntoIterator<Item = usize>,
+    threshold: u32,
+) -> (Vec<Message>, usize) {
+    let mut tokens = estimate_tokens(messages);
+    let mut dropped: Vec<Vec<usize>> = Vec::new();
+    for unit in order {
+        if tokens <= threshold {
+            break;
+        }
+        tokens = tokens.saturating_sub(unit_tokens(messages, &units[unit]));
+        dropped.push(units[unit].clone());
+    }
+    let omitted: usize = dropped.iter().map(Vec::len).sum();
+    (
+        replace_units(messages, &dropped, omission_message(omitted)),
+        omitted,
+    )
+}
+
+fn sliding_window(
+    messages: &[Message],
+    units: &[Vec<usize>],
+    threshold: u32,
+) -> (Vec<Message>, usize) {
+    drop_until_under(messages, units, 0..units.len(), threshold)
+}
+
+fn by_importance(
+    messages: &[Message],
+    units: &[Vec<usize>],
+    threshold: u32,
+) -> (Vec<Message>, usize) {
+    let query = messages
+        .iter()
+        .rev()
+        .find_map(|m| match m {
+            Message::User { content, .. } => Some(words(&content_text(content))),
+            _ => None,
+        })
+        .unwrap_or_default();
+    let scores: Vec<f64> = units
+        .iter()
+        .enumerate()
+        .map(|(position, unit)| importance(messages, unit, position, units.len(), &query))
+        .collect();
+    let mut order: Vec<usize> = (0..units.len()).collect();
+    // Stable sort: equally important units are dropped oldest first
+    order.sort_by(|&a, &b| scores[a].total_cmp(&scores[b]));
+    drop_until_under(messages, units, order, threshold)
+}
+
+/// Score a unit from 0 to 1: half overlap with the latest user message,
+/// 30% recency, 20% role.
+fn importance(
+    messages: &[Message],
+    unit: &[usize],
+    position: usize,
+    count: usize,
+    query: &HashSet<String>,
+) -> f64 {
+    let relevance = if query.is_empty() {
+        0.0
+    } else {
+        let text = unit
+            .iter()
+            .map(|&i| render_message(&messages[i]))
+            .collect::<Vec<_>>()
+            .join(" ");
+        words(&text).intersection(query).count() as f64 / query.len() as f64
+    };
+    let recency = (position + 1) as f64 / count as f64;
+    let role = match messages[unit[0]] {
+        Message::User { .. } => 1.0,
+        Message::Assistant { .. } => 0.6,
+        _ => 0.3,
+    };
+    0.5 * relevance + 0.3 * recency + 0.2 * role
+}
+
+/// Lowercase words of three or more characters.
+fn words(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.chars().count() >= 3)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Remove the messages in `units`, inserting `note` in their place after
+/// the leading system and developer messages.
+fn replace_units(messages: &[Message], units: &[Vec<usize>], note: Message) -> Vec<Message> {
+    let removed: HashSet<usize> = units.iter().flatten().copied().collect();
+    let insert_at = messages.iter().take_while(|m| is_instruction(m)).count();
+    let mut out = Vec::with_capacity(messages.len() - removed.len() + 1);
+    for (i, message) in messages.iter().enumerate() {
+        if i == insert_at && !removed.is_empty() {
+            out.push(note.clone());
+        }
+        if !removed.contains(&i) {
+            out.push(message.clone());
+        }
+    }
+    out
+}
+
+fn omission_message(count: usize) -> Message {
+    Message::System {
+        content: MessageContent::Text(format!(
+            "[{count} earlier message(s) were omitted to fit the context window.]"
+        )),
+        name: None,
+    }
+}
+
+fn summary_message(summary: &str) -> Message {
+    Message::System {
+        content: MessageContent::Text(format!(
+            "Summary of the earlier conversation:\n{}",
+            summary.trim()
+        )),
+        name: None,
+    }
+}
+
+fn render_message(message: &Message) -> String {
+    match message {
+        Message::System { content, .. } => format!("system: {}", content_text(content)),
+        Message::Developer { content, .. } => format!("developer: {}", content_text(content)),
+        Message::User { content, .. } => format!("user: {}", content_text(content)),
+        Message::Tool { content, .. } => format!("tool_result: {}", content_text(content)),
+        Message::Assistant {
+            content,
+            tool_calls,
+            ..
+        } => {
+            let mut lines = Vec::new();
+            if let Some(content) = content {
+                lines.push(format!("assistant: {}", content_text(content)));
+            }
+            for call in tool_calls.iter().flatten() {
+                lines.push(format!(
+                    "tool_call: {} {}",
+                    call.function.name, call.function.arguments
+                ));
+            }
+            lines.join("\n")
+        }
+    }
+}
+
+/// Summarize the compressible units with one non-streaming call.
+async fn summarize(
+    state: &AppState,
+    config: &ContextCompressionConfig,
+    messages: &[Message],
+    units: &[Vec<usize>],
+    route: &RequestRoute<'_>,
+) -> Result<String, String> {
+    let transcript = units
+        .iter()
+        .flatten()
+        .map(|&i| render_message(&messages[i]))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let (provider_name, provider_config, model) = match config.summary_model.as_deref() {
+        Some(summary_model) => {
+            let routed = route_model_extended(Some(summary_model), &state.config.providers)
+                .map_err(|e| format!("routing summary_model failed: {e}"))?;
+            let resolved = resolver::resolve_to_provider(
+                routed,
+                state.db.as_ref(),
+                state.cache.as_ref(),
+                state.secrets.as_ref(),
+                &state.provider_settings,
+                None,
+            )
+            .await
+            .map_err(|e| format!("routing summary_model failed: {e}"))?;
+            (
+                resolved.provider_name,
+                resolved.provider_config,
+                resolved.model,
+            )
+        }
+        None => (
+            route.provider_name.to_string(),
+            route.provider_config.clone(),
+            route.model_name.to_string(),
+        ),
+    };
+
+    let payload = CreateChatCompletionPayload {
+        messages: vec![
+            Message::System {
+                content: MessageContent::Text(config.summary_prompt.clone()),
+                name: None,
+            },
+            Message::User {
+                content: MessageContent::Text(transcript),
+                name: None,
+            },
+        ],
+        model: Some(model),
+        stream: false,
+        temperature: Some(0.2),
+        response_format: None,
+        models: None,
+        frequency_penalty: None,
+        logit_bias: None,
+        logprobs: None,
+        top_logprobs: None,
+        max_completion_tokens: Some(config.summary_max_tokens),
+        max_tokens: None,
+        metadata: None,
+        presence_penalty: None,
+        reasoning: None,
+        seed: None,
+        stop: None,
+        stream_options: None,
+        tool_choice: None,
+        tools: None,
+        top_p: None,
+        user: None,
+        sovereignty_requirements: None,
+    };
+
+    let call = async {
+        let response = execute_provider::<ChatCompletionExecutor>(
+            state,
+            &provider_name,
+            &provider_config,
+            payload,
+            route.tenant,
+        )
+        .await
+        .map_err(|e| format!("provider call failed: {e:?}"))?;
+        if !response.status().is_success() {
+            return Err(format!("provider returned {}", response.status()));
+        }
+        axum::body::to_bytes(response.into_body(), MAX_SUMMARY_RESPONSE_BYTES)
+            .await
+            .map_err(|e| format!("failed to read body: {e}"))
+    };
+    let bytes = tokio::time::timeout(Duration::from_secs(config.summary_timeout_secs), call)
+        .await
+        .map_err(|_| format!("timed out after {}s", config.summary_timeout_secs))??;
+
+    let body: Value = serde_json::from_slice(&bytes).map_err(|e| e.to_string())?;
+    body.pointer("/choices/0/message/content")
+        .and_then(Value::as_str)
+        .filter(|s| !s.trim().is_empty())
+        .map(String::from)
+        .ok_or_else(|| "summary response had no content".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn conversation(messages: Value) -> Vec<Message> {
+        serde_json::from_value(messages).unwrap()
+    }
+
+    fn texts(messages: &[Message]) -> Vec<String> {
+        messages.iter().map(render_message).collect()
+    }
+
+    #[test]
+    fn test_estimate_tokens_counts_text_and_tool_calls() {
+        let messages = conversation(json!([
+            {"role": "user", "content": "abcdefgh"},
+            {"role": "assistant", "content": null, "tool_calls": [
+                {"id": "c1", "type": "function", "function": {"name": "f", "arguments": "{}"}}
+            ]}
+        ]));
+        assert_eq!(estimate_tokens(&messages), 3);
+    }
+
+    #[test]
+    fn test_units_keep_tool_results_with_their_call() {
+        let messages = conversation(json!([
+            {"role": "system", "content": "Be brief"},
+            {"role": "user", "content": "Weather?"},
+            {"role": "assistant", "content": null, "tool_calls": [
+                {"id": "c1", "type": "function", "function": {"name": "weather", "arguments": "{}"}}
+            ]},
+            {"role": "tool", "tool_call_id": "c1", "content": "Sunny"},
+            {"role": "assistant", "content": "It is sunny"},
+            {"role": "user", "content": "Thanks"}
+        ]));
+        assert_eq!(
+            compressible_units(&messages, 2),
+            vec![vec![1], vec![2, 3]],
+            "system messages and the recent window are never compressible"
+        );
+        // A window starting at a tool result is widened to include its call
+        assert_eq!(compressible_units(&messages, 3), vec![vec![1]]);
+        assert!(compressible_units(&messages, 5).is_empty());
+    }
+
+    #[test]
+    fn test_sliding_window_drops_oldest_first() {
+        let long = "x".repeat(400);
+        let messages = conversation(json!([
+            {"role": "system", "content": "Be brief"},
+            {"role": "user", "content": format!("first {long}")},
+            {"role": "assistant", "content": format!("second {long}")},
+            {"role": "user", "content": "latest"}
+        ]));
+        let units = compressible_units(&messages, 1);
+        let (compressed, dropped) = sliding_window(&messages, &units, 150);
+        assert_eq!(dropped, 1);
+        assert_eq!(
+            texts(&compressed),
+            vec![
+                "system: Be brief".to_string(),
+                "system: [1 earlier message(s) were omitted to fit the context window.]"
+                    .to_string(),
+                format!("assistant: second {long}"),
+                "user: latest".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_importance_keeps_relevant_turns() {
+        let long = "x".repeat(400);
+        let messages = conversation(json!([
+            {"role": "user", "content": format!("The deployment region is eu-west {long}")},
+            {"role": "assistant", "content": format!("Unrelated chatter about lunch {long}")},
+            {"role": "user", "content": "Which deployment region did we pick?"}
+        ]));
+        let units = compressible_units(&messages, 1);
+        let (compressed, dropped) = by_importance(&messages, &units, 150);
+        assert_eq!(dropped, 1);
+        let rendered = texts(&compressed).join("\n");
+        assert!(rendered.contains("eu-west"));
+        assert!(!rendered.contains("lunch"));
+    }
+
+    #[test]
+    fn test_replace_units_inserts_summary_after_instructions() {
+        let messages = conversation(json!([
+            {"role": "developer", "content": "Rules"},
+            {"role": "user", "content": "a"},
+            {"role": "assistant", "content": "b"},
+            {"role": "user", "content": "c"}
+        ]));
+        let units = compressible_units(&messages, 1);
+        let compressed = replace_units(&messages, &units, summary_message("We said a, b"));
+        assert_eq!(
+            texts(&compressed),
+            vec![
+                "developer: Rules",
+                "system: Summary of the earlier conversation:\nWe said a, b",
+                "user: c"
+            ]
+        );
+    }
+}
@@ -1,12 +1,15 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use uuid::Uuid;
 
+use super::FieldEncryptor;
 use crate::{
+    api_types::chat_completion::{self, ContentPart, ImageUrl, MessageContent},
     db::{DbPool, DbResult, ListParams, ListResult},
     models::{
-        AppendMessages, Conversation, ConversationOwnerType, ConversationWithProject,
-        CreateConversation, Message, UpdateConversation,
+        AppendMessages, Conversation, ConversationBranch, ConversationOwnerType,
+        ConversationWithProject, CreateConversation, ForkConversation, Message,
+        SetConversationSummary, UpdateConversation,
     },
 };
 
@@ -14,21 +17,72 @@ use crate::{
 #[derive(Clone)]
 pub struct ConversationService {
     db: Arc<DbPool>,
+    encryption: Option<Arc<FieldEncryptor>>,
 }
 
 impl ConversationService {
     pub fn new(db: Arc<DbPool>) -> Self {
-        Self { db }
+        Self {
+            db,
+            encryption: None,
+        }
+    }
+
+    /// Encrypt message content and summaries at rest with the given encryptor.
+    ///
+    /// Content is encrypted on every write and decrypted on every read, so
+    /// callers always see plaintext.
+    pub fn with_encryption(mut self, encryption: Option<Arc<FieldEncryptor>>) -> Self {
+        self.encryption = encryption;
+        self
+    }
+
+    fn encrypt_messages(&self, messages: &mut [Message]) -> DbResult<()> {
+        if let Some(enc) = &self.encryption {
+            for message in messages {
+                message.content = enc.encrypt(&message.content)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn decrypt_messages(&self, messages: &mut [Message]) -> DbResult<()> {
+        if let Some(enc) = &self.encryption {
+            for message in messages {
+                message.content = enc.decrypt(&message.content)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn decrypt_in_place(&self, conversation: &mut Conversation) -> DbResult<()> {
+        self.decrypt_messages(&mut conversation.messages)?;
+        if let (Some(enc), Some(summary)) = (&self.encryption, conversation.summary.as_mut()) {
+            *summary = enc.decrypt(summary)?;
+        }
+        Ok(())
+    }
+
+    fn decrypt(&self, mut conversation: Conversation) -> DbResult<Conversation> {
+        self.decrypt_in_place(&mut conversation)?;
+        Ok(conversation)
+    }
+
+    fn decrypt_opt(&self, conversation: Option<Conversation>) -> DbResult<Option<Conversation>> {
+        conversation.map(|c| self.decrypt(c)).transpose()
     }
 
     /// Create a new conversation
-    pub async fn create(&self, input: CreateConversation) -> DbResult<Conversation> {
-        self.db.conversations().create(input).await
+    pub async fn create(&self, mut input: CreateConversation) -> DbResult<Conversation> {
+        self.encrypt_messages(&mut input.messages)?;
+        let conversation = self.db.conversations().create(input).await?;
+        self.decrypt(conversation)
     }
 
     /// Get conversation by ID
     pub async fn get_by_id(&self, id: Uuid) -> DbResult<Option<Conversation>> {
-        self.db.conversations().get_by_id(id).await
+        let conversation = self.db.conversations().get_by_id(id).await?;
+        self.decrypt_opt(conversation)
     }
 
     /// Get conversation by ID, scoped to a specific organization.
@@ -39,7 +93,12 @@ impl ConversationService {
         id: Uuid,
         org_id: Uuid,
     ) -> DbResult<Option<Conversation>> {
-        self.db.conversations().get_by_id_and_org(id, org_id).await
+        let conversation = self
+            .db
+            .conversations()
+            .get_by_id_and_org(id, org_id)
+            .await?;
+        self.decrypt_opt(conversation)
     }
 
     /// List conversations by owner (project or user)
@@ -49,10 +108,15 @@ impl ConversationService {
         owner_id: Uuid,
         params: ListParams,
     ) -> DbResult<ListResult<Conversation>> {
-        self.db
+        let mut result = self
+            .db
             .conversations()
             .list_by_owner(owner_type, owner_id, params)
-            .await
+            .await?;
+        for conversation in &mut result.items {
+            self.decrypt_in_place(conversation)?;
+        }
+        Ok(result)
     }
 
     /// List conversations by project
@@ -101,13 +165,24 @@ impl ConversationService {
     }
 
     /// Update a conversation
-    pub async fn update(&self, id: Uuid, input: UpdateConversation) -> DbResult<Conversation> {
-        self.db.conversations().update(id, input).await
+    pub async fn update(&self, id: Uuid, mut input: UpdateConversation) -> DbResult<Conversation> {
+        if let Some(messages) = input.messages.as_mut() {
+            self.encrypt_messages(messages)?;
+        }
+        let conversation = self.db.conversations().update(id, input).await?;
+        self.decrypt(conversation)
     }
 
     /// Append messages to a conversation
-    pub async fn append_messages(&self, id: Uuid, input: AppendMessages) -> DbResult<Vec<Message>> {
-        self.db.conversations().append_messages(id, input).await
+    pub async fn append_messages(
+        &self,
+        id: Uuid,
+        mut input: AppendMessages,
+    ) -> DbResult<Vec<Message>> {
+        self.encrypt_messages(&mut input.messages)?;
+        let mut messages = self.db.conversations().append_messages(id, input).await?;
+        self.decrypt_messages(&mut messages)?;
+        Ok(messages)
     }
 
     /// Delete (soft-delete) a conversation
@@ -128,10 +203,15 @@ impl ConversationService {
         limit: i64,
         include_deleted: bool,
     ) -> DbResult<Vec<ConversationWithProject>> {
-        self.db
+        let mut conversations = self
+            .db
             .conversations()
             .list_accessible_for_user(user_id, limit, include_deleted)
-            .await
+            .await?;
+        for item in &mut conversations {
+            self.decrypt_in_place(&mut item.conversation)?;
+        }
+        Ok(conversations)
     }
 
     /// Set the pin order for a conversation
@@ -139,6 +219,198 @@ impl ConversationService {
     /// - `pin_order = Some(n)`: Pin at position n (0 = first)
     /// - `pin_order = None`: Unpin the conversation
     pub async fn set_pin_order(&self, id: Uuid, pin_order: Option<i32>) -> DbResult<Conversation> {
-        self.db.conversations().set_pin_order(id, pin_order).await
+        let conversation = self.db.conversations().set_pin_order(id, pin_order).await?;
+        self.decrypt(conversation)
+    }
+
+    /// List a conversation's branches, oldest first
+    pub async fn list_branches(&self, id: Uuid) -> DbResult<Vec<ConversationBranch>> {
+        let mut branches = self.db.conversations().list_branches(id).await?;
+        for branch in &mut branches {
+            self.decrypt_messages(&mut branch.messages)?;
+        }
+        Ok(branches)
+    }
+
+    /// Edit or delete a message, forking the conversation into a new active branch
+    pub async fn fork(
+        &self,
+        id: Uuid,
+        mut input: ForkConversation,
+    ) -> DbResult<ConversationBranch> {
+        if let Some(message) = input.message.as_mut() {
+            self.encrypt_messages(std::slice::from_mut(message))?;
+        }
+        let mut branch = self.db.conversations().fork(id, input).await?;
+        self.decrypt_messages(&mut branch.messages)?;
+        Ok(branch)
+    }
+
+    /// Switch the conversation to another branch
+    pub async fn switch_branch(&self, id: Uuid, branch_id: Uuid) -> DbResult<Conversation> {
+        let conversation = self.db.conversations().switch_branch(id, branch_id).await?;
+        self.decrypt(conversation)
+    }
+
+    /// List conversations whose background summary is missing or stale.
+    ///
+    /// `org_id = None` selects personal conversations.
+    pub async fn list_needing_summary(
+        &self,
+        org_id: Option<Uuid>,
+        min_messages: u32,
+        resummarize_after: u32,
+        limit: i64,
+    ) -> DbResult<Vec<Conversation>> {
+        let mut conversations = self
+            .db
+            .conversations()
+            .list_needing_summary(org_id, min_messages, resummarize_after, limit)
+            .await?;
+        for conversation in &mut conversations {
+            self.decrypt_in_place(conversation)?;
+        }
+        Ok(conversations)
+    }
+
+    /// Store a generated summary and title on a conversation
+    pub async fn set_summary(&self, id: Uuid, mut input: SetConversationSummary) -> DbResult<()> {
+        if let Some(enc) = &self.encryption {
+            input.summary = enc.encrypt(&input.summary)?;
+        }
+        self.db.conversations().set_summary(id, input).await
+    }
+
+    /// Convert stored messages into Chat Completions messages for replaying
+    /// the conversation to a model.
+    ///
+    /// Image attachments with an entry in `inline_images` (file ID to data URL)
+    /// become `image_url` parts on user messages. Every other attachment is
+    /// mentioned by name in the message text so the model knows it existed.
+    pub fn replay_messages(
+        messages: &[Message],
+        inline_images: &HashMap<Uuid, String>,
+    ) -> Vec<chat_completion::Message> {
+        messages
+            .iter()
+            .map(|message| {
+                let is_user =
+                    !matches!(message.role.as_str(), "assistant" | "system" | "developer");
+                let mut text = message.content.clone();
+                let mut images = Vec::new();
+                for attachment in &message.attachments {
+                    match inline_images.get(&attachment.file_id) {
+                        Some(url) if is_user => images.push(ContentPart::ImageUrl {
+                            image_url: ImageUrl {
+                                url: url.clone(),
+                                detail: None,
+                            },
+                            cache_control: None,
+                        }),
+                        _ => {
+                            let name = attachment.filename.as_deref().unwrap_or("file");
+                            text.push_str(&format!("\n\n[Attached file: {name}]"));
+                        }
+                    }
+                }
+
+                let content = if images.is_empty() {
+                    MessageContent::Text(text)
+                } else {
+                    let mut parts = vec![ContentPart::Text {
+                        text,
+                        cache_control: None,
+                    }];
+                    parts.extend(images);
+                    MessageContent::Parts(parts)
+                };
+
+                match message.role.as_str() {
+                    "assistant" => chat_completion::Message::Assistant {
+                        content: Some(content),
+                        name: None,
+                        tool_calls: None,
+                        refusal: None,
+                        reasoning: None,
+                    },
+                    "system" => chat_completion::Message::System {
+                        content,
+                        name: None,
+                    },
+                    "developer" => chat_completion::Message::Developer {
+                        content,
+                        name: None,
+                    },
+                    // Tool results can't be replayed without their call, so
+                    // anything else is treated as user input.
+                    _ => chat_completion::Message::User {
+                        content,
+                        name: None,
+                    },
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::MessageAttachment;
+
+    fn message(role: &str, content: &str, attachments: Vec<MessageAttachment>) -> Message {
+        Message {
+            role: role.to_string(),
+            content: content.to_string(),
+            annotations: vec![],
+            attachments,
+        }
+    }
+
+    fn attachment(filename: &str, content_type: &str) -> MessageAttachment {
+        MessageAttachment {
+            file_id: Uuid::new_v4(),
+            filename: Some(filename.to_string()),
+            content_type: Some(content_type.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_replay_messages_inlines_images_on_user_messages() {
+        let image = attachment("cat.png", "image/png");
+        let pdf = attachment("report.pdf", "application/pdf");
+        let messages = vec![
+            message("user", "What is this?", vec![image.clone(), pdf]),
+            message("assistant", "A cat.", vec![]),
+        ];
+        let inline = HashMap::from([(image.file_id, "data:image/png;base64,AAAA".to_string())]);
+
+        let replay = ConversationService::replay_messages(&messages, &inline);
+        let json = serde_json::to_value(&replay).unwrap();
+
+        assert_eq!(json[0]["role"], "user");
+        assert_eq!(json[0]["content"][0]["type"], "text");
+        assert_eq!(
+            json[0]["content"][0]["text"],
+            "What is this?\n\n[Attached file: report.pdf]"
+        );
+        assert_eq!(json[0]["content"][1]["type"], "image_url");
+        assert_eq!(
+            json[0]["content"][1]["image_url"]["url"],
+            "data:image/png;base64,AAAA"
+        );
+        assert_eq!(json[1]["role"], "assistant");
+        assert_eq!(json[1]["content"], "A cat.");
+    }
+
+    #[test]
+    fn test_replay_messages_without_inline_images_mentions_files() {
+        let image = attachment("cat.png", "image/png");
+        let messages = vec![message("user", "Look", vec![image])];
+
+        let replay = ConversationService::replay_messages(&messages, &HashMap::new());
+        let json = serde_json::to_value(&replay).unwrap();
+
+        assert_eq!(json[0]["content"], "Look\n\n[Attached file: cat.png]");
     }
 }
@@ -0,0 +1,307 @@
+//! Time-boxed elevation of a member's organization role (break-glass access).
+//!
+//! A member requests a role with a justification; once approved by someone
+//! else, their membership role is changed for `duration_hours`. The role they
+//! held at approval is remembered and restored when the elevation is revoked
+//! or expires (see [`ElevationService::expire_due`]).
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    config::ElevationsConfig,
+    db::{DbError, DbPool, DbResult, ListParams, repos::ListResult},
+    models::{
+        AuditActorType, CreateAuditLog, CreateRoleElevation, ElevationStatus, NewRoleElevation,
+        RoleElevation,
+    },
+};
+
+/// Expired elevations reverted per batch.
+const EXPIRY_BATCH: i64 = 100;
+
+/// Service layer for organization role elevations
+#[derive(Clone)]
+pub struct ElevationService {
+    db: Arc<DbPool>,
+}
+
+impl ElevationService {
+    pub fn new(db: Arc<DbPool>) -> Self {
+        Self { db }
+    }
+
+    /// The member's current role in the organization, if they are a member.
+    pub async fn current_role(&self, org_id: Uuid, user_id: Uuid) -> DbResult<Option<String>> {
+        let memberships = self
+            .db
+            .users()
+            .get_org_memberships_for_user(user_id)
+            .await?;
+        Ok(memberships
+            .into_iter()
+            .find(|m| m.org_id == org_id)
+            .map(|m| m.role))
+    }
+
+    /// File a pending request to elevate `user_id` in the organization.
+    ///
+    /// Fails with `Validation` if the role can't be requested, the duration is
+    /// over the limit, or the user isn't a member already holding another
+    /// role, and with `Conflict` if they already have an open request.
+    pub async fn request(
+        &self,
+        config: &ElevationsConfig,
+        org_id: Uuid,
+        user_id: Uuid,
+        input: CreateRoleElevation,
+        requested_by: Option<Uuid>,
+    ) -> DbResult<RoleElevation> {
+        if !config.roles.contains(&input.role) {
+            return Err(DbError::Validation(format!(
+                "Role '{}' can't be requested; allowed roles: {}",
+                input.role,
+                config.roles.join(", ")
+            )));
+        }
+        if input.duration_hours > config.max_duration_hours {
+            return Err(DbError::Validation(format!(
+                "duration_hours must be at most {}",
+                config.max_duration_hours
+            )));
+        }
+        match self.current_role(org_id, user_id).await? {
+            None => {
+                return Err(DbError::Validation(
+                    "Only organization members can be elevated".to_string(),
+                ));
+            }
+            Some(role) if role == input.role => {
+                return Err(DbError::Validation(format!(
+                    "The member already has the '{}' role",
+                    role
+                )));
+            }
+            Some(_) => {}
+        }
+
+        self.db
+            .role_elevations()
+            .create(NewRoleElevation {
+                org_id,
+                user_id,
+                role: input.role,
+                justification: input.justification,
+                duration_hours: input.duration_hours as i32,
+                requested_by,
+            })
+            .await
+    }
+
+    /// Get elevation by ID
+    pub async fn get_by_id(&self, id: Uuid) -> DbResult<Option<RoleElevation>> {
+        self.db.role_elevations().get_by_id(id).await
+    }
+
+    /// List an organization's elevations with pagination
+    pub async fn list_by_org(
+        &self,
+        org_id: Uuid,
+        params: ListParams,
+    ) -> DbResult<ListResult<RoleElevation>> {
+        self.db.role_elevations().list_by_org(org_id, params).await
+    }
+
+    /// Approve a pending elevation and grant the role until
+    /// `now + duration_hours`.
+    ///
+    /// The elevation is marked active before the membership changes, so a
+    /// concurrent approval can't grant it twice. If the membership update
+    /// fails, the elevation is ended as revoked.
+    pub async fn approve(
+        &self,
+        elevation: &RoleElevation,
+        decided_by: Option<Uuid>,
+        note: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> DbResult<RoleElevation> {
+        let previous_role = self
+            .current_role(elevation.org_id, elevation.user_id)
+            .await?
+            .ok_or_else(|| {
+                DbError::Validation("The user is no longer an organization member".to_string())
+            })?;
+        let expires_at = now + Duration::hours(elevation.duration_hours as i64);
+
+        let approved = self
+            .db
+            .role_elevations()
+            .approve(elevation.id, decided_by, note, &previous_role, expires_at)
+            .await?;
+
+        if let Err(e) = self
+            .db
+            .users()
+            .update_org_member_role(approved.user_id, approved.org_id, &approved.role)
+            .await
+        {
+            let _ = self
+                .db
+                .role_elevations()
+                .end(
+                    approved.id,
+                    ElevationStatus::Active,
+                    ElevationStatus::Revoked,
+                    None,
+                )
+                .await;
+            return Err(e);
+        }
+
+        Ok(approved)
+    }
+
+    /// Deny a pending elevation
+    pub async fn deny(
+        &self,
+        id: Uuid,
+        decided_by: Option<Uuid>,
+        note: Option<&str>,
+    ) -> DbResult<RoleElevation> {
+        self.db.role_elevations().deny(id, decided_by, note).await
+    }
+
+    /// Withdraw a pending elevation, or end an active one early and restore
+    /// the previous role. Fails with `Conflict` if it has already ended.
+    pub async fn revoke(
+        &self,
+        elevation: &RoleElevation,
+        ended_by: Option<Uuid>,
+    ) -> DbResult<RoleElevation> {
+        let from = match elevation.status {
+            ElevationStatus::Pending | ElevationStatus::Active => elevation.status,
+            status => {
+                return Err(DbError::Conflict(format!(
+                    "Elevation is already {}",
+                    status.as_str()
+                )));
+            }
+        };
+
+        let ended = self
+            .db
+            .role_elevations()
+            .end(elevation.id, from, ElevationStatus::Revoked, ended_by)
+            .await?;
+        if !ended {
+            return Err(DbError::Conflict(
+                "Elevation changed while it was being revoked".to_string(),
+            ));
+        }
+        if from == ElevationStatus::Active {
+            self.restore(elevation).await?;
+        }
+
+        self.get_by_id(elevation.id).await?.ok_or(DbError::NotFound)
+    }
+
+    /// Revert every active elevation whose `expires_at` has passed, writing
+    /// an `elevation.expire` audit entry for each. Returns how many were
+    /// reverted.
+    ///
+    /// Each elevation is claimed by moving it to `expired` before its role is
+    /// restored, so concurrent runs never restore the same one twice.
+    pub async fn expire_due(&self, now: DateTime<Utc>) -> DbResult<u64> {
+        let mut expired = 0;
+        loop {
+            let due = self
+                .db
+                .role_elevations()
+                .list_expired(now, EXPIRY_BATCH)
+                .await?;
+            let batch_len = due.len() as i64;
+
+            for elevation in due {
+                let claimed = self
+                    .db
+                    .role_elevations()
+                    .end(
+                        elevation.id,
+                        ElevationStatus::Active,
+                        ElevationStatus::Expired,
+                        None,
+                    )
+                    .await?;
+                if !claimed {
+                    continue;
+                }
+
+                let restored = match self.restore(&elevation).await {
+                    Ok(restored) => restored,
+                    Err(e) => {
+                        tracing::error!(
+                            elevation_id = %elevation.id,
+                            error = %e,
+                            "Failed to restore role after elevation expired"
+                        );
+                        false
+                    }
+                };
+                expired += 1;
+
+                let _ = self
+                    .db
+                    .audit_logs()
+                    .create(CreateAuditLog {
+                        actor_type: AuditActorType::System,
+                        actor_id: None,
+                        action: "elevation.expire".to_string(),
+                        resource_type: "role_elevation".to_string(),
+                        resource_id: elevation.id,
+                        org_id: Some(elevation.org_id),
+                        project_id: None,
+                        details: json!({
+                            "user_id": elevation.user_id,
+                            "role": elevation.role,
+                            "restored_role": elevation.previous_role,
+                            "restored": restored,
+                            "expires_at": elevation.expires_at,
+                        }),
+                        ip_address: None,
+                        user_agent: None,
+                    })
+                    .await;
+            }
+
+            if batch_len < EXPIRY_BATCH {
+                break;
+            }
+        }
+        Ok(expired)
+    }
+
+    /// Put back the role the member held before the elevation. Leaves the
+    /// membership alone if the member has left or their role was changed
+    /// since, so a manual change isn't overwritten. Returns whether the role
+    /// was restored.
+    async fn restore(&self, elevation: &RoleElevation) -> DbResult<bool> {
+        let Some(previous_role) = &elevation.previous_role else {
+            return Ok(false);
+        };
+        let current = self
+            .current_role(elevation.org_id, elevation.user_id)
+            .await?;
+        if current.as_deref() != Some(elevation.role.as_str()) {
+            return Ok(false);
+        }
+
+        self.db
+            .users()
+            .update_org_member_role(elevation.user_id, elevation.org_id, previous_role)
+            .await?;
+        Ok(true)
+    }
+}
@@ -0,0 +1,190 @@
+//! SMTP email delivery.
+//!
+//! Thin wrapper around `lettre`'s async SMTP transport, configured from
+//! `[notifications.email]`. Used by scheduled reports to mail rendered
+//! reports as attachments, and by
+//! [`EmailNotifier`](super::notifications::EmailNotifier) for notification
+//! emails.
+
+use std::time::Duration;
+
+use lettre::{
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+    message::{Attachment, Mailbox, MultiPart, SinglePart, header::ContentType},
+    transport::smtp::authentication::Credentials,
+};
+use thiserror::Error;
+
+use crate::config::{EmailConfig, SmtpTls};
+
+#[derive(Debug, Error)]
+pub enum EmailError {
+    #[error("Invalid email address '{address}': {reason}")]
+    InvalidAddress { address: String, reason: String },
+
+    #[error("Failed to build email: {0}")]
+    Build(String),
+
+    #[error("SMTP transport error: {0}")]
+    Transport(String),
+}
+
+/// A file attached to an outgoing email.
+#[derive(Debug, Clone)]
+pub struct EmailAttachment {
+    pub filename: String,
+    pub content_type: String,
+    pub content: Vec<u8>,
+}
+
+/// An email ready to send.
+#[derive(Debug, Clone, Default)]
+pub struct OutgoingEmail {
+    pub to: Vec<String>,
+    pub subject: String,
+    /// Plain-text body.
+    pub text: String,
+    pub attachments: Vec<EmailAttachment>,
+}
+
+/// Sends email through the configured SMTP relay.
+#[derive(Clone)]
+pub struct EmailSender {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl EmailSender {
+    pub fn new(config: &EmailConfig) -> Result<Self, EmailError> {
+        let builder = match config.tls {
+            SmtpTls::Starttls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host),
+            SmtpTls::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host),
+            SmtpTls::None => Ok(AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(
+                &config.host,
+            )),
+        }
+        .map_err(|e| EmailError::Transport(e.to_string()))?;
+
+        let mut builder = builder
+            .port(config.port)
+            .timeout(Some(Duration::from_secs(config.timeout_secs)));
+        if let (Some(username), Some(password)) = (&config.username, &config.password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+
+        Ok(Self {
+            transport: builder.build(),
+            from: parse_mailbox(&config.from)?,
+        })
+    }
+
+    /// Send an email to all recipients in a single SMTP transaction.
+    pub async fn send(&self, email: OutgoingEmail) -> Result<(), EmailError> {
+        let message = build_message(&self.from, email)?;
+        self.deliver(message).await
+    }
+
+    /// Send an email from a sender other than the configured `from`.
+    pub async fn send_from(&self, from: &str, email: OutgoingEmail) -> Result<(), EmailError> {
+        let message = build_message(&parse_mailbox(from)?, email)?;
+        self.deliver(message).await
+    }
+
+    async fn deliver(&self, message: Message) -> Result<(), EmailError> {
+        self.transport
+            .send(message)
+            .await
+            .map_err(|e| EmailError::Transport(e.to_string()))?;
+        Ok(())
+    }
+}
+
+fn parse_mailbox(address: &str) -> Result<Mailbox, EmailError> {
+    address.parse().map_err(
+        |e: lettre::address::AddressError| EmailError::InvalidAddress {
+            address: address.to_string(),
+            reason: e.to_string(),
+        },
+    )
+}
+
+fn build_message(from: &Mailbox, email: OutgoingEmail) -> Result<Message, EmailError> {
+    if email.to.is_empty() {
+        return Err(EmailError::Build("no recipients".into()));
+    }
+
+    let mut builder = Message::builder().from(from.clone()).subject(email.subject);
+    for to in &email.to {
+        builder = builder.to(parse_mailbox(to)?);
+    }
+
+    let message = if email.attachments.is_empty() {
+        builder.singlepart(SinglePart::plain(email.text))
+    } else {
+        let mut multipart = MultiPart::mixed().singlepart(SinglePart::plain(email.text));
+        for attachment in email.attachments {
+            let content_type = ContentType::parse(&attachment.content_type)
+                .map_err(|e| EmailError::Build(e.to_string()))?;
+            multipart = multipart.singlepart(
+                Attachment::new(attachment.filename).body(attachment.content, content_type),
+            );
+        }
+        builder.multipart(multipart)
+    };
+
+    message.map_err(|e| EmailError::Build(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from() -> Mailbox {
+        parse_mailbox("Hadrian <noreply@example.com>").unwrap()
+    }
+
+    #[test]
+    fn test_build_message_with_attachment() {
+        let message = build_message(
+            &from(),
+            OutgoingEmail {
+                to: vec!["ops@example.com".into(), "finance@example.com".into()],
+                subject: "Weekly usage".into(),
+                text: "See attached.".into(),
+                attachments: vec![EmailAttachment {
+                    filename: "usage.csv".into(),
+                    content_type: "text/csv".into(),
+                    content: b"model,cost\ngpt-4o,1.00\n".to_vec(),
+                }],
+            },
+        )
+        .unwrap();
+
+        let raw = String::from_utf8(message.formatted()).unwrap();
+        assert!(raw.contains("Subject: Weekly usage"));
+        assert!(raw.contains("ops@example.com"));
+        assert!(raw.contains("finance@example.com"));
+        assert!(raw.contains("filename=\"usage.csv\""));
+    }
+
+    #[test]
+    fn test_build_message_rejects_invalid_recipient() {
+        let err = build_message(
+            &from(),
+            OutgoingEmail {
+                to: vec!["not an address".into()],
+                subject: "x".into(),
+                text: "x".into(),
+                attachments: vec![],
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, EmailError::InvalidAddress { .. }));
+    }
+
+    #[test]
+    fn test_build_message_requires_recipients() {
+        let err = build_message(&from(), OutgoingEmail::default()).unwrap_err();
+        assert!(matches!(err, EmailError::Build(_)));
+    }
+}
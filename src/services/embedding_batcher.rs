@@ -0,0 +1,745 @@
+//! Batching for `/v1/embeddings` per `[features.embeddings]`.
+//!
+//! Two optimizations sit between the embeddings handler and the provider:
+//!
+//! - **Fan-out**: an input longer than the provider's batch size is split
+//!   into batches sent concurrently, up to `max_concurrency` at a time. The
+//!   responses are stitched back together in input order, with `index`
+//!   renumbered and usage summed. If any batch fails, the request fails with
+//!   that batch's error.
+//! - **Coalescing**: small text requests for the same provider, model and
+//!   options that arrive within `window_ms` of each other are sent as one
+//!   provider request. The first request of a batch leads it: it waits out
+//!   the window (or until the batch is full), makes the call, and hands every
+//!   other request its slice of the result. Usage is shared in proportion to
+//!   each request's input length. If the leader goes away before sending,
+//!   the others fall back to sending their own requests.
+
+use std::{
+    collections::HashMap,
+    ops::Range,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use axum::{body::Body, response::Response};
+use futures::{StreamExt, TryStreamExt, stream};
+use http::{StatusCode, header::CONTENT_LENGTH};
+use serde_json::Value;
+use tokio::sync::{Notify, oneshot};
+
+use crate::{
+    AppState,
+    api_types::embeddings::{CreateEmbeddingPayload, EmbeddingInput},
+    config::{EmbeddingsCoalesceConfig, EmbeddingsConfig, ProviderConfig, SovereigntyRequirements},
+    providers::Tenant,
+    routes::{
+        api::ApiError,
+        execution::{EmbeddingExecutor, ExecutionResult, execute_with_fallback},
+    },
+};
+
+/// Inputs per request for providers without a configured batch size.
+fn default_batch_size(provider_config: &ProviderConfig) -> usize {
+    match provider_config.provider_type_name() {
+        // Titan embeds one text per upstream call, so smaller batches mean
+        // more of them run in parallel
+        "bedrock" => 16,
+        "vertex" => 250,
+        _ => 2048,
+    }
+}
+
+/// The provider an embeddings request was routed to.
+pub struct EmbeddingTarget {
+    pub provider_name: String,
+    pub provider_config: ProviderConfig,
+    pub model_name: String,
+    /// Whether requests from different callers may share a provider request:
+    /// only for providers from the config file, never for a caller's own
+    /// dynamic provider.
+    pub shared: bool,
+}
+
+/// Splits and coalesces embedding requests per `[features.embeddings]`.
+pub struct EmbeddingBatcher {
+    config: EmbeddingsConfig,
+    /// Coalescing batches still accepting requests, by [`coalesce_key`].
+    open: Mutex<HashMap<String, Arc<Batch>>>,
+}
+
+/// A coalesced batch being collected.
+struct Batch {
+    key: String,
+    state: Mutex<BatchState>,
+    /// Signalled when the batch is full, so the leader sends it early.
+    full: Notify,
+}
+
+#[derive(Default)]
+struct BatchState {
+    inputs: Vec<String>,
+    /// Requests that joined after the leader.
+    followers: Vec<Follower>,
+    closed: bool,
+}
+
+struct Follower {
+    range: Range<usize>,
+    tx: oneshot::Sender<Result<ExecutionResult, ApiError>>,
+}
+
+enum Role {
+    Leader(LeaderGuard),
+    Follower(oneshot::Receiver<Result<ExecutionResult, ApiError>>),
+}
+
+/// Closes the leader's batch if the leader is dropped before sending it,
+/// so followers stop waiting and send their own requests.
+struct LeaderGuard {
+    batcher: Arc<EmbeddingBatcher>,
+    batch: Arc<Batch>,
+}
+
+impl Drop for LeaderGuard {
+    fn drop(&mut self) {
+        self.batcher.close(&self.batch);
+    }
+}
+
+impl EmbeddingBatcher {
+    /// Build the batcher, or `None` when `[features.embeddings]` is disabled.
+    pub fn new(config: &EmbeddingsConfig) -> Option<Self> {
+        config.enabled.then(|| Self {
+            config: config.clone(),
+            open: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Inputs per provider request for `provider_name`.
+    pub fn batch_size(&self, provider_name: &str, provider_config: &ProviderConfig) -> usize {
+        self.config
+            .batch_sizes
+            .get(provider_name)
+            .copied()
+            .unwrap_or_else(|| default_batch_size(provider_config))
+    }
+
+    /// Send an embeddings request, splitting or coalescing it as configured.
+    pub async fn execute(
+        self: &Arc<Self>,
+        state: &AppState,
+        target: EmbeddingTarget,
+        payload: CreateEmbeddingPayload,
+        sovereignty: Option<&SovereigntyRequirements>,
+        tenant: &Tenant,
+    ) -> Result<ExecutionResult, ApiError> {
+        let batch_size = self.batch_size(&target.provider_name, &target.provider_config);
+        if input_len(&payload.input) > batch_size {
+            return self
+                .fan_out(state, target, payload, batch_size, sovereignty, tenant)
+                .await;
+        }
+        if let Some(coalesce) = &self.config.coalesce
+            && target.shared
+            && let Some(texts) = texts(&payload.input)
+            && texts.len() < coalesce.max_inputs.min(batch_size)
+        {
+            return self
+                .coalesce(state, target, payload, texts, coalesce, sovereignty, tenant)
+                .await;
+        }
+        execute_with_fallback::<EmbeddingExecutor>(
+            state,
+            target.provider_name,
+            target.provider_config,
+            target.model_name,
+            payload,
+            sovereignty,
+            tenant,
+        )
+        .await
+    }
+
+    /// Send the input in batches of `batch_size` and merge the responses.
+    async fn fan_out(
+        &self,
+        state: &AppState,
+        target: EmbeddingTarget,
+        payload: CreateEmbeddingPayload,
+        batch_size: usize,
+        sovereignty: Option<&SovereigntyRequirements>,
+        tenant: &Tenant,
+    ) -> Result<ExecutionResult, ApiError> {
+        let batches = split_input(&payload.input, batch_size);
+        tracing::debug!(
+            provider = %target.provider_name,
+            model = %target.model_name,
+            batches = batches.len(),
+            batch_size,
+            "Splitting embeddings request"
+        );
+
+        let results: Vec<ExecutionResult> = stream::iter(batches.into_iter().map(|input| {
+            execute_with_fallback::<EmbeddingExecutor>(
+                state,
+                target.provider_name.clone(),
+                target.provider_config.clone(),
+                target.model_name.clone(),
+                CreateEmbeddingPayload {
+                    input,
+                    ..payload.clone()
+                },
+                sovereignty,
+                tenant,
+            )
+        }))
+        .buffered(self.config.max_concurrency)
+        .try_collect()
+        .await?;
+
+        // A failed batch fails the request as it is
+        if let Some(position) = results
+            .iter()
+            .position(|r| !r.response.status().is_success())
+        {
+            return Ok(results
+                .into_iter()
+                .nth(position)
+                .expect("position is in range"));
+        }
+
+        let mut results = results.into_iter();
+        let first = results.next().expect("at least one batch");
+        let (parts, body) = first.response.into_parts();
+        let mut bodies = vec![read_json(state, body).await?];
+        for result in results {
+            bodies.push(read_json(state, result.response.into_body()).await?);
+        }
+
+        Ok(ExecutionResult {
+            response: json_response(parts, &merge_responses(bodies)),
+            provider_name: first.provider_name,
+            model_name: first.model_name,
+        })
+    }
+
+    /// Join or lead a coalesced batch.
+    #[allow(clippy::too_many_arguments)]
+    async fn coalesce(
+        self: &Arc<Self>,
+        state: &AppState,
+        target: EmbeddingTarget,
+        payload: CreateEmbeddingPayload,
+        texts: Vec<String>,
+        coalesce: &EmbeddingsCoalesceConfig,
+        sovereignty: Option<&SovereigntyRequirements>,
+        tenant: &Tenant,
+    ) -> Result<ExecutionResult, ApiError> {
+        let key = coalesce_key(&target, &payload, sovereignty);
+        let own = 0..texts.len();
+
+        let guard = match self.join(key, texts, coalesce.max_inputs) {
+            Role::Leader(guard) => guard,
+            Role::Follower(rx) => {
+                return match rx.await {
+                    Ok(result) => result,
+                    // The leader went away; send this request on its own
+                    Err(_) => {
+                        execute_with_fallback::<EmbeddingExecutor>(
+                            state,
+                            target.provider_name,
+                            target.provider_config,
+                            target.model_name,
+                            payload,
+                            sovereignty,
+                            tenant,
+                        )
+                        .await
+                    }
+                };
+            }
+        };
+
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_millis(coalesce.window_ms)) => {}
+            _ = guard.batch.full.notified() => {}
+        }
+        let (inputs, followers) = self.close(&guard.batch);
+        if followers.is_empty() {
+            return execute_with_fallback::<EmbeddingExecutor>(
+                state,
+                target.provider_name,
+                target.provider_config,
+                target.model_name,
+                payload,
+                sovereignty,
+                tenant,
+            )
+            .await;
+        }
+
+        tracing::debug!(
+            provider = %target.provider_name,
+            model = %target.model_name,
+            requests = followers.len() + 1,
+            inputs = inputs.len(),
+            "Sending coalesced embeddings request"
+        );
+        let weights: Vec<usize> = std::iter::once(&own)
+            .chain(followers.iter().map(|f| &f.range))
+            .map(|range| inputs[range.clone()].iter().map(|t| t.len().max(1)).sum())
+            .collect();
+        let combined = CreateEmbeddingPayload {
+            input: EmbeddingInput::TextArray(inputs),
+            ..payload
+        };
+        let result = execute_with_fallback::<EmbeddingExecutor>(
+            state,
+            target.provider_name,
+            target.provider_config,
+            target.model_name,
+            combined,
+            sovereignty,
+            tenant,
+        )
+        .await;
+
+        let result = match result {
+            Ok(result) => result,
+            Err(e) => {
+                for follower in followers {
+                    let _ = follower.tx.send(Err(e.clone()));
+                }
+                return Err(e);
+            }
+        };
+        let ExecutionResult {
+            response,
+            provider_name,
+            model_name,
+        } = result;
+        let (parts, body) = response.into_parts();
+        let share = |response| ExecutionResult {
+            response,
+            provider_name: provider_name.clone(),
+            model_name: model_name.clone(),
+        };
+
+        // An error response goes to everyone as it is
+        if !parts.status.is_success() {
+            let bytes =
+                match axum::body::to_bytes(body, state.config.server.max_response_body_bytes).await
+                {
+                    Ok(bytes) => bytes,
+                    Err(e) => {
+                        let error = unreadable_response(e);
+                        for follower in followers {
+                            let _ = follower.tx.send(Err(error.clone()));
+                        }
+                        return Err(error);
+                    }
+                };
+            for follower in followers {
+                let response = Response::from_parts(parts.clone(), Body::from(bytes.clone()));
+                let _ = follower.tx.send(Ok(share(response)));
+            }
+            return Ok(share(Response::from_parts(parts, Body::from(bytes))));
+        }
+
+        let body = match read_json(state, body).await {
+            Ok(body) => body,
+            Err(error) => {
+                for follower in followers {
+                    let _ = follower.tx.send(Err(error.clone()));
+                }
+                return Err(error);
+            }
+        };
+        let total_weight: usize = weights.iter().sum();
+        let mut weight_before = weights[0];
+        for (follower, weight) in followers.into_iter().zip(&weights[1..]) {
+            let slice = slice_response(
+                &body,
+                follower.range,
+                weight_before..weight_before + weight,
+                total_weight,
+            );
+            weight_before += weight;
+            let _ = follower
+                .tx
+                .send(Ok(share(json_response(parts.clone(), &slice))));
+        }
+        let own_slice = slice_response(&body, own, 0..weights[0], total_weight);
+        Ok(share(json_response(parts, &own_slice)))
+    }
+
+    /// Add `texts` to the open batch for `key`, or open a new one led by
+    /// this request.
+    fn join(self: &Arc<Self>, key: String, texts: Vec<String>, max_inputs: usize) -> Role {
+        let mut open = self.open.lock().expect("embedding batches lock");
+        if let Some(batch) = open.get(&key) {
+            let mut state = batch.state.lock().expect("embedding batch lock");
+            if !state.closed && state.inputs.len() + texts.len() <= max_inputs {
+                let start = state.inputs.len();
+                state.inputs.extend(texts);
+                let (tx, rx) = oneshot::channel();
+                state.followers.push(Follower {
+                    range: start..state.inputs.len(),
+                    tx,
+                });
+                if state.inputs.len() >= max_inputs {
+                    // Full: stop accepting and let the leader send it now
+                    state.closed = true;
+                    drop(state);
+                    if let Some(batch) = open.remove(&key) {
+                        batch.full.notify_one();
+                    }
+                }
+                return Role::Follower(rx);
+            }
+        }
+
+        // No batch, or no room in it: this request starts the next one
+        let batch = Arc::new(Batch {
+            key: key.clone(),
+            state: Mutex::new(BatchState {
+                inputs: texts,
+                ..Default::default()
+            }),
+            full: Notify::new(),
+        });
+        open.insert(key, Arc::clone(&batch));
+        Role::Leader(LeaderGuard {
+            batcher: Arc::clone(self),
+            batch,
+        })
+    }
+
+    /// Stop `batch` accepting requests and take what it collected. Later
+    /// calls get nothing.
+    fn close(&self, batch: &Arc<Batch>) -> (Vec<String>, Vec<Follower>) {
+        let mut open = self.open.lock().expect("embedding batches lock");
+        if open.get(&batch.key).is_some_and(|b| Arc::ptr_eq(b, batch)) {
+            open.remove(&batch.key);
+        }
+        drop(open);
+        let mut state = batch.state.lock().expect("embedding batch lock");
+        state.closed = true;
+        (
+            std::mem::take(&mut state.inputs),
+            std::mem::take(&mut state.followers),
+        )
+    }
+}
+
+/// Requests coalesce only when everything but their input matches.
+fn coalesce_key(
+    target: &EmbeddingTarget,
+    payload: &CreateEmbeddingPayload,
+    sovereignty: Option<&SovereigntyRequirements>,
+) -> String {
+    let options = serde_json::json!({
+        "encoding_format": payload.encoding_format,
+        "dimensions": payload.dimensions,
+        "user": payload.user,
+        "provider": payload.provider,
+        "input_type": payload.input_type,
+        "sovereignty": sovereignty,
+    });
+    format!(
+        "{}\n{}\n{}",
+        target.provider_name, target.model_name, options
+    )
+}
+
+/// Number of embeddings the input asks for.
+fn input_len(input: &EmbeddingInput) -> usize {
+    match input {
+        EmbeddingInput::Text(_) | EmbeddingInput::Tokens(_) => 1,
+        EmbeddingInput::TextArray(texts) => texts.len(),
+        EmbeddingInput::TokenArrays(arrays) => arrays.len(),
+        EmbeddingInput::Multimodal(items) => items.len(),
+    }
+}
+
+/// The input as texts, when it is text.
+fn texts(input: &EmbeddingInput) -> Option<Vec<String>> {
+    match input {
+        EmbeddingInput::Text(text) => Some(vec![text.clone()]),
+        EmbeddingInput::TextArray(texts) => Some(texts.clone()),
+        _ => None,
+    }
+}
+
+/// Split a list input into inputs of at most `batch_size` items.
+fn split_input(input: &EmbeddingInput, batch_size: usize) -> Vec<EmbeddingInput> {
+    match input {
+        EmbeddingInput::TextArray(texts) => texts
+            .chunks(batch_size)
+            .map(|c| EmbeddingInput::TextArray(c.to_vec()))
+            .collect(),
+        EmbeddingInput::TokenArrays(arrays) => arrays
+            .chunks(batch_size)
+            .map(|c| EmbeddingInput::TokenArrays(c.to_vec()))
+            .collect(),
+        EmbeddingInput::Multimodal(items) => items
+            .chunks(batch_size)
+            .map(|c| EmbeddingInput::Multimodal(c.to_vec()))
+            .collect(),
+        EmbeddingInput::Text(_) | EmbeddingInput::Tokens(_) => vec![input.clone()],
+    }
+}
+
+/// Position of an embedding within its response.
+fn data_index(item: &Value, position: usize) -> usize {
+    item.get("index")
+        .and_then(Value::as_f64)
+        .map(|i| i as usize)
+        .unwrap_or(position)
+}
+
+/// A response's embeddings, ordered by their `index`.
+fn sorted_data(body: &Value) -> Vec<(usize, Value)> {
+    let mut items: Vec<_> = body
+        .get("data")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .enumerate()
+        .map(|(position, item)| (data_index(item, position), item.clone()))
+        .collect();
+    items.sort_by_key(|(index, _)| *index);
+    items
+}
+
+/// Stitch batch responses together in order: `data` concatenated and
+/// renumbered, usage summed, everything else from the first response.
+fn merge_responses(bodies: Vec<Value>) -> Value {
+    let mut data = Vec::new();
+    let mut usage = [0u64; 2];
+    for body in &bodies {
+        let offset = data.len();
+        for (position, (_, mut item)) in sorted_data(body).into_iter().enumerate() {
+            item["index"] = Value::from(offset + position);
+            data.push(item);
+        }
+        for (total, field) in usage.iter_mut().zip(["prompt_tokens", "total_tokens"]) {
+            *total += usage_field(body, field);
+        }
+    }
+
+    let mut merged = bodies.into_iter().next().unwrap_or_default();
+    if let Some(object) = merged.as_object_mut() {
+        object.insert("data".to_string(), Value::Array(data));
+        if object.contains_key("usage") {
+            object.insert(
+                "usage".to_string(),
+                serde_json::json!({"prompt_tokens": usage[0], "total_tokens": usage[1]}),
+            );
+        }
+    }
+    merged
+}
+
+/// One request's share of a coalesced response: the embeddings for its
+/// `range` of inputs, renumbered from 0, and the part of the usage that its
+/// `weights` (a range of the cumulative input length) covers.
+fn slice_response(
+    body: &Value,
+    range: Range<usize>,
+    weights: Range<usize>,
+    total_weight: usize,
+) -> Value {
+    let data: Vec<Value> = sorted_data(body)
+        .into_iter()
+        .filter(|(index, _)| range.contains(index))
+        .map(|(index, mut item)| {
+            item["index"] = Value::from(index - range.start);
+            item
+        })
+        .collect();
+
+    let mut slice = body.clone();
+    if let Some(object) = slice.as_object_mut() {
+        object.insert("data".to_string(), Value::Array(data));
+        if body.get("usage").is_some() {
+            // Cumulative rounding, so the shares add up to the total
+            let share = |field| {
+                let total = usage_field(body, field) as u128;
+                let upto = |w: usize| (total * w as u128 / total_weight.max(1) as u128) as u64;
+                upto(weights.end) - upto(weights.start)
+            };
+            object.insert(
+                "usage".to_string(),
+                serde_json::json!({
+                    "prompt_tokens": share("prompt_tokens"),
+                    "total_tokens": share("total_tokens"),
+                }),
+            );
+        }
+    }
+    slice
+}
+
+fn usage_field(body: &Value, field: &str) -> u64 {
+    body.get("usage")
+        .and_then(|u| u.get(field))
+        .and_then(Value::as_f64)
+        .map(|v| v.max(0.0).round() as u64)
+        .unwrap_or(0)
+}
+
+async fn read_json(state: &AppState, body: Body) -> Result<Value, ApiError> {
+    let bytes = axum::body::to_bytes(body, state.config.server.max_response_body_bytes)
+        .await
+        .map_err(unreadable_response)?;
+    serde_json::from_slice(&bytes).map_err(unreadable_response)
+}
+
+fn unreadable_response(error: impl std::fmt::Display) -> ApiError {
+    ApiError::new(
+        StatusCode::BAD_GATEWAY,
+        "provider_error",
+        format!("Failed to read embeddings response: {error}"),
+    )
+}
+
+fn json_response(mut parts: http::response::Parts, body: &Value) -> Response {
+    parts.headers.remove(CONTENT_LENGTH);
+    Response::from_parts(
+        parts,
+        Body::from(serde_json::to_vec(body).unwrap_or_default()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_split_input() {
+        let input = EmbeddingInput::TextArray((0..5).map(|i| i.to_string()).collect());
+        let batches = split_input(&input, 2);
+        assert_eq!(batches.len(), 3);
+        assert!(matches!(&batches[2], EmbeddingInput::TextArray(t) if t == &["4"]));
+        assert_eq!(split_input(&EmbeddingInput::Text("a".into()), 2).len(), 1);
+        assert_eq!(
+            input_len(&EmbeddingInput::TokenArrays(vec![vec![1.0]; 3])),
+            3
+        );
+    }
+
+    #[test]
+    fn test_merge_responses() {
+        let merged = merge_responses(vec![
+            json!({
+                "object": "list",
+                "model": "m",
+                "data": [
+                    {"object": "embedding", "index": 1, "embedding": [1.0]},
+                    {"object": "embedding", "index": 0, "embedding": [0.0]}
+                ],
+                "usage": {"prompt_tokens": 3, "total_tokens": 3}
+            }),
+            json!({
+                "object": "list",
+                "model": "m",
+                "data": [{"object": "embedding", "index": 0, "embedding": [2.0]}],
+                "usage": {"prompt_tokens": 2, "total_tokens": 2}
+            }),
+        ]);
+        let embeddings: Vec<_> = merged["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|d| {
+                (
+                    d["index"].as_u64().unwrap(),
+                    d["embedding"][0].as_f64().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(embeddings, vec![(0, 0.0), (1, 1.0), (2, 2.0)]);
+        assert_eq!(
+            merged["usage"],
+            json!({"prompt_tokens": 5, "total_tokens": 5})
+        );
+        assert_eq!(merged["model"], "m");
+    }
+
+    #[test]
+    fn test_slice_response_shares_usage() {
+        let body = json!({
+            "object": "list",
+            "data": (0..4).map(|i| json!({"object": "embedding", "index": i, "embedding": [i as f64]})).collect::<Vec<_>>(),
+            "usage": {"prompt_tokens": 10, "total_tokens": 10}
+        });
+        // Weights 1, 1 and 1 over inputs 0..1, 1..3 and 3..4
+        let slices = [
+            slice_response(&body, 0..1, 0..1, 3),
+            slice_response(&body, 1..3, 1..2, 3),
+            slice_response(&body, 3..4, 2..3, 3),
+        ];
+        assert_eq!(slices[1]["data"].as_array().unwrap().len(), 2);
+        assert_eq!(slices[1]["data"][1]["index"], 1);
+        assert_eq!(slices[1]["data"][1]["embedding"][0], 2.0);
+        let tokens: u64 = slices
+            .iter()
+            .map(|s| s["usage"]["prompt_tokens"].as_u64().unwrap())
+            .sum();
+        assert_eq!(tokens, 10);
+    }
+
+    #[tokio::test]
+    async fn test_join_collects_followers_until_full() {
+        let batcher = Arc::new(
+            EmbeddingBatcher::new(&EmbeddingsConfig {
+                enabled: true,
+                ..Default::default()
+            })
+            .unwrap(),
+        );
+        let Role::Leader(leader) = batcher.join("k".into(), vec!["a".into()], 3) else {
+            panic!("first request leads");
+        };
+        let Role::Follower(_first) = batcher.join("k".into(), vec!["b".into()], 3) else {
+            panic!("second request follows");
+        };
+        // Too big for the open batch: starts the next one
+        let Role::Leader(next) = batcher.join("k".into(), vec!["c".into(), "d".into()], 3) else {
+            panic!("no room, so it leads");
+        };
+        let Role::Follower(_second) = batcher.join("k".into(), vec!["e".into()], 3) else {
+            panic!("joins the newest batch");
+        };
+        // The newest batch is full and no longer open
+        assert!(batcher.open.lock().unwrap().get("k").is_none());
+        next.batch.full.notified().await;
+
+        let (inputs, followers) = batcher.close(&leader.batch);
+        assert_eq!(inputs, vec!["a", "b"]);
+        assert_eq!(followers[0].range, 1..2);
+        assert!(batcher.close(&leader.batch).0.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_followers_notified_when_leader_dropped() {
+        let batcher = Arc::new(
+            EmbeddingBatcher::new(&EmbeddingsConfig {
+                enabled: true,
+                ..Default::default()
+            })
+            .unwrap(),
+        );
+        let leader = batcher.join("k".into(), vec!["a".into()], 8);
+        let Role::Follower(rx) = batcher.join("k".into(), vec!["b".into()], 8) else {
+            panic!("second request follows");
+        };
+        drop(leader);
+        assert!(rx.await.is_err());
+        assert!(batcher.open.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,121 @@
+//! Organization plans and feature entitlements (`[features.entitlements]`).
+//!
+//! Data-plane features (semantic cache, file search, batch uploads) are
+//! checked where requests are handled; the project and API key limits are
+//! checked here, before the admin API creates either.
+
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    config::EntitlementsConfig,
+    db::{DbError, DbPool, DbResult},
+    models::{ApiKeyOwner, EntitlementUsage, Entitlements, Organization},
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum EntitlementError {
+    #[error("Database error: {0}")]
+    Database(#[from] DbError),
+
+    #[error("{0}")]
+    NotEntitled(String),
+}
+
+/// Service layer for organization entitlements
+#[derive(Clone)]
+pub struct EntitlementService {
+    db: Arc<DbPool>,
+}
+
+impl EntitlementService {
+    pub fn new(db: Arc<DbPool>) -> Self {
+        Self { db }
+    }
+
+    /// The organization's entitlements, or `None` when entitlements aren't
+    /// enforced and everything is allowed.
+    pub fn for_org(config: &EntitlementsConfig, org: &Organization) -> Option<Entitlements> {
+        config
+            .enabled
+            .then(|| Entitlements::resolve(config, org.entitlements.as_ref()))
+    }
+
+    /// Projects and active API keys counted against the organization's limits.
+    pub async fn usage(&self, org_id: Uuid) -> DbResult<EntitlementUsage> {
+        Ok(EntitlementUsage {
+            projects: self.db.projects().count_by_org(org_id, false).await?,
+            api_keys: self.db.api_keys().count_active_by_org(org_id).await?,
+        })
+    }
+
+    /// Fail with `NotEntitled` if the organization's plan doesn't allow
+    /// another project.
+    pub async fn check_project_limit(
+        &self,
+        config: &EntitlementsConfig,
+        org: &Organization,
+    ) -> Result<(), EntitlementError> {
+        let Some(entitlements) = Self::for_org(config, org) else {
+            return Ok(());
+        };
+        if entitlements.max_projects == 0 {
+            return Ok(());
+        }
+        let projects = self.db.projects().count_by_org(org.id, false).await?;
+        entitlements
+            .check_projects(projects)
+            .map_err(EntitlementError::NotEntitled)
+    }
+
+    /// Fail with `NotEntitled` if the plan of the organization `owner`
+    /// belongs to doesn't allow another API key. User-owned keys aren't
+    /// attributed to an organization and are never limited here.
+    pub async fn check_api_key_limit(
+        &self,
+        config: &EntitlementsConfig,
+        owner: &ApiKeyOwner,
+    ) -> Result<(), EntitlementError> {
+        if !config.enabled {
+            return Ok(());
+        }
+        let Some(org_id) = self.owner_org_id(owner).await? else {
+            return Ok(());
+        };
+        let Some(org) = self.db.organizations().get_by_id(org_id).await? else {
+            return Ok(());
+        };
+        let entitlements = Entitlements::resolve(config, org.entitlements.as_ref());
+        if entitlements.max_api_keys == 0 {
+            return Ok(());
+        }
+        let api_keys = self.db.api_keys().count_active_by_org(org_id).await?;
+        entitlements
+            .check_api_keys(api_keys)
+            .map_err(EntitlementError::NotEntitled)
+    }
+
+    /// The organization a key owner belongs to.
+    async fn owner_org_id(&self, owner: &ApiKeyOwner) -> DbResult<Option<Uuid>> {
+        Ok(match owner {
+            ApiKeyOwner::Organization { org_id } => Some(*org_id),
+            ApiKeyOwner::Team { team_id } => {
+                self.db.teams().get_by_id(*team_id).await?.map(|t| t.org_id)
+            }
+            ApiKeyOwner::Project { project_id } => self
+                .db
+                .projects()
+                .get_by_id(*project_id)
+                .await?
+                .map(|p| p.org_id),
+            ApiKeyOwner::ServiceAccount { service_account_id } => self
+                .db
+                .service_accounts()
+                .get_by_id(*service_account_id)
+                .await?
+                .map(|sa| sa.org_id),
+            ApiKeyOwner::User { .. } => None,
+        })
+    }
+}
@@ -0,0 +1,203 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+use chrono::{DateTime, NaiveDate, Utc};
+
+use crate::{
+    db::{DateRange, DbPool, DbResult},
+    models::{
+        FederatedDailyUsage, FederatedGateway, FederatedGatewayUsage, FederatedModelUsage,
+        FederatedUsageRow, FederatedUsageSummary, FederatedUsageTotals, FederationReport,
+    },
+};
+
+/// Service layer for the federation hub: stores satellite reports and
+/// aggregates them into cross-gateway views.
+#[derive(Clone)]
+pub struct FederationService {
+    db: Arc<DbPool>,
+}
+
+impl FederationService {
+    pub fn new(db: Arc<DbPool>) -> Self {
+        Self { db }
+    }
+
+    /// Store a report from an authenticated satellite
+    pub async fn record_report(
+        &self,
+        report: &FederationReport,
+        received_at: DateTime<Utc>,
+    ) -> DbResult<()> {
+        self.db
+            .federation()
+            .record_report(report, received_at)
+            .await
+    }
+
+    /// List all satellites that have reported
+    pub async fn list_gateways(&self) -> DbResult<Vec<FederatedGateway>> {
+        self.db.federation().list_gateways().await
+    }
+
+    /// Get a single satellite
+    pub async fn get_gateway(&self, id: &str) -> DbResult<Option<FederatedGateway>> {
+        self.db.federation().get_gateway(id).await
+    }
+
+    /// Forget a decommissioned satellite and its usage
+    pub async fn delete_gateway(&self, id: &str) -> DbResult<bool> {
+        self.db.federation().delete_gateway(id).await
+    }
+
+    /// Aggregate reported usage over an inclusive date range
+    pub async fn usage_summary(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        gateway_id: Option<&str>,
+    ) -> DbResult<FederatedUsageSummary> {
+        let rows = self
+            .db
+            .federation()
+            .list_usage(
+                DateRange {
+                    start: start_date,
+                    end: end_date,
+                },
+                gateway_id,
+            )
+            .await?;
+        Ok(summarize(start_date, end_date, &rows))
+    }
+}
+
+/// Roll stored rows up into overall, per-gateway, per-model and per-day totals.
+fn summarize(
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    rows: &[FederatedUsageRow],
+) -> FederatedUsageSummary {
+    let mut totals = FederatedUsageTotals::default();
+    let mut by_gateway: HashMap<&str, FederatedUsageTotals> = HashMap::new();
+    let mut by_model: HashMap<&str, FederatedUsageTotals> = HashMap::new();
+    let mut by_date: BTreeMap<NaiveDate, FederatedUsageTotals> = BTreeMap::new();
+
+    for row in rows {
+        totals.add(&row.usage);
+        by_gateway
+            .entry(row.gateway_id.as_str())
+            .or_default()
+            .add(&row.usage);
+        by_model
+            .entry(row.usage.model.as_str())
+            .or_default()
+            .add(&row.usage);
+        by_date.entry(row.usage.date).or_default().add(&row.usage);
+    }
+
+    let mut by_gateway: Vec<_> = by_gateway
+        .into_iter()
+        .map(|(gateway_id, totals)| FederatedGatewayUsage {
+            gateway_id: gateway_id.to_string(),
+            totals,
+        })
+        .collect();
+    by_gateway.sort_by_key(|g| {
+        (
+            Reverse(g.totals.total_cost_microcents),
+            g.gateway_id.clone(),
+        )
+    });
+
+    let mut by_model: Vec<_> = by_model
+        .into_iter()
+        .map(|(model, totals)| FederatedModelUsage {
+            model: model.to_string(),
+            totals,
+        })
+        .collect();
+    by_model.sort_by_key(|m| (Reverse(m.totals.total_cost_microcents), m.model.clone()));
+
+    FederatedUsageSummary {
+        start_date,
+        end_date,
+        totals,
+        by_gateway,
+        by_model,
+        by_date: by_date
+            .into_iter()
+            .map(|(date, totals)| FederatedDailyUsage { date, totals })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::FederatedUsage;
+
+    fn row(gateway_id: &str, day: u32, model: &str, cost: i64) -> FederatedUsageRow {
+        FederatedUsageRow {
+            gateway_id: gateway_id.to_string(),
+            usage: FederatedUsage {
+                date: NaiveDate::from_ymd_opt(2025, 3, day).unwrap(),
+                model: model.to_string(),
+                total_cost_microcents: cost,
+                input_tokens: 10,
+                output_tokens: 5,
+                total_tokens: 15,
+                request_count: 1,
+            },
+        }
+    }
+
+    #[test]
+    fn test_summarize() {
+        let start = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2025, 3, 2).unwrap();
+        let rows = vec![
+            row("eu-west-1", 1, "gpt-4o", 100),
+            row("eu-west-1", 2, "claude-sonnet", 50),
+            row("us-east-1", 2, "gpt-4o", 300),
+        ];
+
+        let summary = summarize(start, end, &rows);
+        assert_eq!(summary.totals.total_cost_microcents, 450);
+        assert_eq!(summary.totals.request_count, 3);
+        assert_eq!(summary.totals.total_tokens, 45);
+
+        let gateways: Vec<_> = summary
+            .by_gateway
+            .iter()
+            .map(|g| (g.gateway_id.as_str(), g.totals.total_cost_microcents))
+            .collect();
+        assert_eq!(gateways, vec![("us-east-1", 300), ("eu-west-1", 150)]);
+
+        let models: Vec<_> = summary
+            .by_model
+            .iter()
+            .map(|m| (m.model.as_str(), m.totals.total_cost_microcents))
+            .collect();
+        assert_eq!(models, vec![("gpt-4o", 400), ("claude-sonnet", 50)]);
+
+        let days: Vec<_> = summary
+            .by_date
+            .iter()
+            .map(|d| (d.date, d.totals.total_cost_microcents))
+            .collect();
+        assert_eq!(days, vec![(start, 100), (end, 350)]);
+    }
+
+    #[test]
+    fn test_summarize_empty() {
+        let day = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        let summary = summarize(day, day, &[]);
+        assert_eq!(summary.totals, FederatedUsageTotals::default());
+        assert!(summary.by_gateway.is_empty());
+        assert!(summary.by_date.is_empty());
+    }
+}
@@ -0,0 +1,334 @@
+//! Envelope encryption for sensitive columns stored in the database.
+//!
+//! Every value gets its own random 256-bit data key (DEK). The value is
+//! sealed with the DEK using AES-256-GCM, and the DEK is in turn sealed with
+//! a key-encryption key (KEK) loaded from the secrets manager. The stored
+//! envelope looks like:
+//!
+//! ```text
+//! enc:v1:<kek id>:<base64(nonce || wrapped dek)>:<base64(nonce || ciphertext)>
+//! ```
+//!
+//! Values without the `enc:v1:` prefix are treated as legacy plaintext and
+//! returned unchanged, so encryption can be switched on for an existing
+//! deployment without a data migration. Rows are re-encrypted under the
+//! active KEK the next time they are written.
+
+use std::collections::HashMap;
+
+use aes_gcm::{
+    Aes256Gcm, Key, Nonce,
+    aead::{Aead, KeyInit, Payload},
+};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use rand::RngCore;
+use thiserror::Error;
+
+use crate::{config::FieldEncryptionConfig, db::DbError, secrets::SecretManager};
+
+const ENVELOPE_PREFIX: &str = "enc:v1:";
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug, Error)]
+pub enum FieldEncryptionError {
+    #[error("Failed to load encryption key '{key_id}': {reason}")]
+    KeyLoad { key_id: String, reason: String },
+
+    #[error("Unknown encryption key '{0}'")]
+    UnknownKey(String),
+
+    #[error("Malformed encrypted value")]
+    Malformed,
+
+    #[error("Encryption failed")]
+    Encrypt,
+
+    #[error("Decryption failed")]
+    Decrypt,
+}
+
+impl From<FieldEncryptionError> for DbError {
+    fn from(err: FieldEncryptionError) -> Self {
+        DbError::Internal(err.to_string())
+    }
+}
+
+/// Encrypts and decrypts individual field values using envelope encryption.
+pub struct FieldEncryptor {
+    /// KEK id used for new writes. `None` when encryption is disabled for
+    /// writes but existing ciphertext must still be readable.
+    active_key: Option<String>,
+    keys: HashMap<String, Aes256Gcm>,
+}
+
+impl FieldEncryptor {
+    /// Build an encryptor from raw 32-byte KEKs.
+    pub fn new(active_key: Option<String>, keys: HashMap<String, [u8; KEY_LEN]>) -> Self {
+        let keys = keys
+            .into_iter()
+            .map(|(id, key)| (id, Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key))))
+            .collect();
+        Self { active_key, keys }
+    }
+
+    /// Load every configured KEK from the secrets manager.
+    pub async fn from_config(
+        config: &FieldEncryptionConfig,
+        secrets: &dyn SecretManager,
+    ) -> Result<Self, FieldEncryptionError> {
+        let mut keys = HashMap::with_capacity(config.keys.len());
+        for (key_id, secret_name) in &config.keys {
+            let load_err = |reason: String| FieldEncryptionError::KeyLoad {
+                key_id: key_id.clone(),
+                reason,
+            };
+            let encoded = secrets
+                .get(secret_name)
+                .await
+                .map_err(|e| load_err(e.to_string()))?
+                .ok_or_else(|| load_err(format!("secret '{secret_name}' not found")))?;
+            let bytes = STANDARD
+                .decode(encoded.trim())
+                .map_err(|e| load_err(format!("invalid base64: {e}")))?;
+            let key: [u8; KEY_LEN] = bytes
+                .try_into()
+                .map_err(|_| load_err(format!("key must be {KEY_LEN} bytes")))?;
+            keys.insert(key_id.clone(), key);
+        }
+
+        let active_key = config.enabled.then(|| config.active_key.clone());
+        Ok(Self::new(active_key, keys))
+    }
+
+    /// Returns true if `value` is an encrypted envelope.
+    pub fn is_encrypted(value: &str) -> bool {
+        value.starts_with(ENVELOPE_PREFIX)
+    }
+
+    /// Encrypt `plaintext` under the active key. Returns the input unchanged
+    /// when writes are not being encrypted.
+    pub fn encrypt(&self, plaintext: &str) -> Result<String, FieldEncryptionError> {
+        let Some(key_id) = self.active_key.as_deref() else {
+            return Ok(plaintext.to_string());
+        };
+        let kek = self
+            .keys
+            .get(key_id)
+            .ok_or_else(|| FieldEncryptionError::UnknownKey(key_id.to_string()))?;
+
+        let mut dek = [0u8; KEY_LEN];
+        rand::thread_rng().fill_bytes(&mut dek);
+
+        // Bind the wrapped DEK to its KEK id so an envelope can't be
+        // re-labelled to point at a different key.
+        let wrapped = seal(kek, &dek, key_id.as_bytes())?;
+        let dek_cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&dek));
+        let sealed = seal(&dek_cipher, plaintext.as_bytes(), &[])?;
+
+        Ok(format!(
+            "{ENVELOPE_PREFIX}{key_id}:{}:{}",
+            STANDARD.encode(wrapped),
+            STANDARD.encode(sealed)
+        ))
+    }
+
+    /// Decrypt an envelope produced by [`encrypt`](Self::encrypt). Plaintext
+    /// values (no envelope prefix) are passed through.
+    pub fn decrypt(&self, value: &str) -> Result<String, FieldEncryptionError> {
+        let Some(rest) = value.strip_prefix(ENVELOPE_PREFIX) else {
+            return Ok(value.to_string());
+        };
+        let mut parts = rest.splitn(3, ':');
+        let (Some(key_id), Some(wrapped), Some(sealed)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(FieldEncryptionError::Malformed);
+        };
+
+        let kek = self
+            .keys
+            .get(key_id)
+            .ok_or_else(|| FieldEncryptionError::UnknownKey(key_id.to_string()))?;
+        let wrapped = STANDARD
+            .decode(wrapped)
+            .map_err(|_| FieldEncryptionError::Malformed)?;
+        let sealed = STANDARD
+            .decode(sealed)
+            .map_err(|_| FieldEncryptionError::Malformed)?;
+
+        let dek = open(kek, &wrapped, key_id.as_bytes())?;
+        if dek.len() != KEY_LEN {
+            return Err(FieldEncryptionError::Malformed);
+        }
+        let dek_cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&dek));
+        let plaintext = open(&dek_cipher, &sealed, &[])?;
+        String::from_utf8(plaintext).map_err(|_| FieldEncryptionError::Malformed)
+    }
+
+    /// Returns true if `value` should be rewritten: it is plaintext while
+    /// encryption is on, or it was sealed under a key other than the active one.
+    pub fn needs_rotation(&self, value: &str) -> bool {
+        let Some(active) = self.active_key.as_deref() else {
+            return false;
+        };
+        match value.strip_prefix(ENVELOPE_PREFIX) {
+            Some(rest) => rest.split(':').next() != Some(active),
+            None => true,
+        }
+    }
+}
+
+/// Encrypt with a random nonce and return `nonce || ciphertext`.
+fn seal(cipher: &Aes256Gcm, msg: &[u8], aad: &[u8]) -> Result<Vec<u8>, FieldEncryptionError> {
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), Payload { msg, aad })
+        .map_err(|_| FieldEncryptionError::Encrypt)?;
+    let mut out = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+/// Inverse of [`seal`].
+fn open(cipher: &Aes256Gcm, data: &[u8], aad: &[u8]) -> Result<Vec<u8>, FieldEncryptionError> {
+    if data.len() < NONCE_LEN {
+        return Err(FieldEncryptionError::Malformed);
+    }
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    cipher
+        .decrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad,
+            },
+        )
+        .map_err(|_| FieldEncryptionError::Decrypt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secrets::MemorySecretManager;
+
+    fn encryptor(active: &str, ids: &[&str]) -> FieldEncryptor {
+        let keys = ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (id.to_string(), [i as u8 + 1; KEY_LEN]))
+            .collect();
+        FieldEncryptor::new(Some(active.to_string()), keys)
+    }
+
+    #[test]
+    fn round_trip() {
+        let enc = encryptor("k1", &["k1"]);
+        let sealed = enc.encrypt("hello, world").unwrap();
+        assert!(sealed.starts_with("enc:v1:k1:"));
+        assert!(!sealed.contains("hello"));
+        assert_eq!(enc.decrypt(&sealed).unwrap(), "hello, world");
+    }
+
+    #[test]
+    fn each_value_gets_a_fresh_data_key() {
+        let enc = encryptor("k1", &["k1"]);
+        assert_ne!(enc.encrypt("same").unwrap(), enc.encrypt("same").unwrap());
+    }
+
+    #[test]
+    fn plaintext_passes_through() {
+        let enc = encryptor("k1", &["k1"]);
+        assert_eq!(enc.decrypt("legacy value").unwrap(), "legacy value");
+        assert!(enc.needs_rotation("legacy value"));
+    }
+
+    #[test]
+    fn old_keys_remain_readable_after_rotation() {
+        let old = encryptor("k1", &["k1", "k2"]);
+        let sealed = old.encrypt("secret").unwrap();
+
+        let rotated = encryptor("k2", &["k1", "k2"]);
+        assert_eq!(rotated.decrypt(&sealed).unwrap(), "secret");
+        assert!(rotated.needs_rotation(&sealed));
+
+        let resealed = rotated.encrypt("secret").unwrap();
+        assert!(resealed.starts_with("enc:v1:k2:"));
+        assert!(!rotated.needs_rotation(&resealed));
+    }
+
+    #[test]
+    fn unknown_key_is_an_error() {
+        let sealed = encryptor("k1", &["k1"]).encrypt("secret").unwrap();
+        let other = encryptor("k9", &["k9"]);
+        assert!(matches!(
+            other.decrypt(&sealed),
+            Err(FieldEncryptionError::UnknownKey(id)) if id == "k1"
+        ));
+    }
+
+    #[test]
+    fn relabelled_envelope_fails_authentication() {
+        // Same key material under two ids: swapping the id must not decrypt.
+        let keys = HashMap::from([
+            ("a".to_string(), [7; KEY_LEN]),
+            ("b".to_string(), [7; KEY_LEN]),
+        ]);
+        let enc = FieldEncryptor::new(Some("a".to_string()), keys);
+        let sealed = enc.encrypt("secret").unwrap();
+        let tampered = sealed.replacen("enc:v1:a:", "enc:v1:b:", 1);
+        assert!(matches!(
+            enc.decrypt(&tampered),
+            Err(FieldEncryptionError::Decrypt)
+        ));
+    }
+
+    #[test]
+    fn disabled_writes_plaintext_but_still_decrypts() {
+        let sealed = encryptor("k1", &["k1"]).encrypt("secret").unwrap();
+        let keys = HashMap::from([("k1".to_string(), [1; KEY_LEN])]);
+        let disabled = FieldEncryptor::new(None, keys);
+        assert_eq!(disabled.encrypt("plain").unwrap(), "plain");
+        assert_eq!(disabled.decrypt(&sealed).unwrap(), "secret");
+        assert!(!disabled.needs_rotation("plain"));
+    }
+
+    #[tokio::test]
+    async fn loads_keys_from_secret_manager() {
+        let secrets = MemorySecretManager::new();
+        secrets
+            .set("kek/primary", &STANDARD.encode([3u8; KEY_LEN]))
+            .await
+            .unwrap();
+        let config = FieldEncryptionConfig {
+            enabled: true,
+            active_key: "primary".to_string(),
+            keys: HashMap::from([("primary".to_string(), "kek/primary".to_string())]),
+        };
+        let enc = FieldEncryptor::from_config(&config, &secrets)
+            .await
+            .unwrap();
+        let sealed = enc.encrypt("value").unwrap();
+        assert_eq!(enc.decrypt(&sealed).unwrap(), "value");
+    }
+
+    #[tokio::test]
+    async fn rejects_short_keys() {
+        let secrets = MemorySecretManager::new();
+        secrets
+            .set("kek/short", &STANDARD.encode([3u8; 16]))
+            .await
+            .unwrap();
+        let config = FieldEncryptionConfig {
+            enabled: true,
+            active_key: "short".to_string(),
+            keys: HashMap::from([("short".to_string(), "kek/short".to_string())]),
+        };
+        assert!(matches!(
+            FieldEncryptor::from_config(&config, &secrets).await,
+            Err(FieldEncryptionError::KeyLoad { .. })
+        ));
+    }
+}
@@ -21,7 +21,10 @@ use crate::{
     },
     config::{CircuitBreakerConfig, RerankConfig, RetryConfig},
     db::{DbPool, ListParams},
-    models::{AttributeFilter, FileSearchRankingOptions, VectorStore, VectorStoreOwnerType},
+    models::{
+        AttributeFilter, FileSearchRankingOptions, HybridSearchOptions, VectorStore,
+        VectorStoreOwnerType, VectorStoreStatus,
+    },
     providers::{
         circuit_breaker::CircuitBreaker,
         retry::{is_retryable_database_error, with_circuit_breaker_and_retry_generic},
@@ -58,6 +61,10 @@ pub enum FileSearchError {
     #[error("Access denied to vector store: {0}")]
     AccessDenied(Uuid),
 
+    /// VectorStore has expired per its `expires_after` policy.
+    #[error("Vector store has expired: {0}")]
+    VectorStoreExpired(Uuid),
+
     /// Vector stores have incompatible embedding configurations.
     #[error("Incompatible collections: {0}")]
     IncompatibleVectorStores(String),
@@ -104,6 +111,10 @@ pub struct FileSearchResult {
     pub chunk_index: i32,
     /// The actual text content of the chunk.
     pub content: String,
+    /// Character offset of the chunk in the original document.
+    pub char_start: i32,
+    /// Character end offset of the chunk in the original document.
+    pub char_end: i32,
     /// Similarity score (0.0 to 1.0, higher is more similar).
     pub score: f64,
     /// Optional filename (resolved from file metadata).
@@ -272,17 +283,11 @@ impl FileSearchService {
         let vector_store_ids_str: Vec<Uuid> = request.vector_store_ids.clone();
         let vector_store = self.vector_store.clone();
 
-        // Determine if hybrid search should be used
-        let use_hybrid = request
-            .ranking_options
-            .as_ref()
-            .is_some_and(|opts| opts.use_hybrid_search());
+        let hybrid_options =
+            effective_hybrid_options(request.ranking_options.as_ref(), &collections);
 
         // Search with circuit breaker and retry for transient errors
-        let search_results = if use_hybrid {
-            // Build HybridSearchConfig from ranking_options
-            let hybrid_opts = request.ranking_options.as_ref().unwrap();
-            let api_hybrid = hybrid_opts.hybrid_search.as_ref().unwrap();
+        let search_results = if let Some(api_hybrid) = hybrid_options {
             let hybrid_config = HybridSearchConfig {
                 rrf: RrfConfig::weighted(api_hybrid.embedding_weight, api_hybrid.text_weight),
                 vector_threshold: threshold,
@@ -371,6 +376,13 @@ impl FileSearchService {
             results
         };
 
+        // 7. Searching counts as activity for `expires_after` policies
+        for store in &collections {
+            if let Err(e) = self.db.vector_stores().touch_vector_store(store.id).await {
+                tracing::warn!(vector_store_id = %store.id, error = %e, "Failed to touch vector store");
+            }
+        }
+
         Ok(FileSearchResponse {
             results,
             query: request.query,
@@ -516,6 +528,10 @@ impl FileSearchService {
                 }
             }
 
+            if vector_store.status == VectorStoreStatus::Expired {
+                return Err(FileSearchError::VectorStoreExpired(id));
+            }
+
             collections.push(vector_store);
         }
 
@@ -669,6 +685,8 @@ impl FileSearchService {
                 file_id: chunk.file_id,
                 chunk_index: chunk.chunk_index,
                 content: chunk.content,
+                char_start: chunk.char_start,
+                char_end: chunk.char_end,
                 score: chunk.score,
                 filename,
                 metadata: chunk.metadata,
@@ -718,6 +736,26 @@ impl std::fmt::Debug for FileSearchService {
     }
 }
 
+/// Fusion weights for a search, or `None` for vector-only search.
+///
+/// Weights passed in `ranking_options.hybrid_search` win. Otherwise the first searched
+/// store with a `hybrid_search` default supplies them, as long as the ranker allows
+/// hybrid search.
+fn effective_hybrid_options(
+    ranking_options: Option<&FileSearchRankingOptions>,
+    collections: &[VectorStore],
+) -> Option<HybridSearchOptions> {
+    let ranker = ranking_options
+        .map(|opts| opts.effective_ranker())
+        .unwrap_or_default();
+    if !ranker.supports_hybrid() {
+        return None;
+    }
+    ranking_options
+        .and_then(|opts| opts.hybrid_search.clone())
+        .or_else(|| collections.iter().find_map(|vs| vs.hybrid_search.clone()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -850,6 +888,66 @@ mod tests {
         );
     }
 
+    fn store_with_hybrid_defaults(hybrid_search: Option<HybridSearchOptions>) -> VectorStore {
+        let now = chrono::Utc::now();
+        VectorStore {
+            id: Uuid::new_v4(),
+            object: crate::models::OBJECT_TYPE_VECTOR_STORE.to_string(),
+            owner_type: VectorStoreOwnerType::User,
+            owner_id: Uuid::new_v4(),
+            name: "test".to_string(),
+            description: None,
+            status: VectorStoreStatus::Completed,
+            embedding_model: "text-embedding-3-small".to_string(),
+            embedding_dimensions: 1536,
+            usage_bytes: 0,
+            file_counts: Default::default(),
+            metadata: None,
+            expires_after: None,
+            hybrid_search,
+            expires_at: None,
+            last_active_at: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_effective_hybrid_options_falls_back_to_store_defaults() {
+        let stores = vec![
+            store_with_hybrid_defaults(None),
+            store_with_hybrid_defaults(Some(HybridSearchOptions::keyword_focused())),
+        ];
+
+        // No ranking options: the store's weights enable hybrid search
+        assert_eq!(
+            effective_hybrid_options(None, &stores),
+            Some(HybridSearchOptions::keyword_focused())
+        );
+
+        // Request weights take precedence
+        let ranking_options =
+            FileSearchRankingOptions::with_hybrid(0.0, HybridSearchOptions::semantic_focused());
+        assert_eq!(
+            effective_hybrid_options(Some(&ranking_options), &stores),
+            Some(HybridSearchOptions::semantic_focused())
+        );
+
+        // A vector-only ranker ignores store defaults
+        let ranking_options =
+            FileSearchRankingOptions::with_ranker(0.0, crate::models::FileSearchRanker::Vector);
+        assert_eq!(
+            effective_hybrid_options(Some(&ranking_options), &stores),
+            None
+        );
+
+        // Stores without defaults stay vector-only
+        assert_eq!(
+            effective_hybrid_options(None, &[store_with_hybrid_defaults(None)]),
+            None
+        );
+    }
+
     #[test]
     fn test_file_search_error_rerank_display() {
         let err = FileSearchError::RerankError("timeout".to_string());
@@ -1007,6 +1105,7 @@ mod access_control_tests {
                     embedding_dimensions: 1536,
                     metadata: None,
                     expires_after: None,
+                    hybrid_search: None,
                     chunking_strategy: None,
                 })
                 .await
@@ -1469,6 +1568,7 @@ mod access_control_tests {
                 embedding_dimensions: 1536,
                 metadata: None,
                 expires_after: None,
+                hybrid_search: None,
                 chunking_strategy: None,
             })
             .await
@@ -1536,6 +1636,7 @@ mod access_control_tests {
                 embedding_dimensions: 1536,
                 metadata: None,
                 expires_after: None,
+                hybrid_search: None,
                 chunking_strategy: None,
             })
             .await
@@ -1625,6 +1726,7 @@ mod access_control_tests {
                 embedding_dimensions: 1536,
                 metadata: None,
                 expires_after: None,
+                hybrid_search: None,
                 chunking_strategy: None,
             })
             .await
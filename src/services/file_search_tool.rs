@@ -596,11 +596,17 @@ pub struct CitationTracker {
     sources: HashMap<usize, SourceInfo>,
 }
 
-/// Information about a source file from search results.
+/// Information about a source chunk from search results.
 #[derive(Debug, Clone)]
 pub struct SourceInfo {
     pub file_id: Uuid,
     pub filename: String,
+    pub vector_store_id: Uuid,
+    pub chunk_id: Uuid,
+    /// Character range of the chunk in the source file.
+    pub char_start: i32,
+    pub char_end: i32,
+    pub score: f64,
 }
 
 impl CitationTracker {
@@ -626,6 +632,11 @@ impl CitationTracker {
                         .filename
                         .clone()
                         .unwrap_or_else(|| result.file_id.to_string()),
+                    vector_store_id: result.vector_store_id,
+                    chunk_id: result.chunk_id,
+                    char_start: result.char_start,
+                    char_end: result.char_end,
+                    score: result.score,
                 },
             );
         }
@@ -644,7 +655,8 @@ impl CitationTracker {
     /// Parse citation markers from text and generate FileCitation annotations.
     ///
     /// Scans the text for patterns like `[Source 1]`, `[Source 2]`, etc. and creates
-    /// `FileCitation` annotations with the file information from the tracker.
+    /// `FileCitation` annotations with the file and chunk information from the
+    /// tracker.
     ///
     /// Returns a list of annotations sorted by their position in the text.
     pub fn parse_citations(&self, text: &str) -> Vec<ResponsesAnnotation> {
@@ -668,6 +680,11 @@ impl CitationTracker {
                     file_id: source_info.file_id.to_string(),
                     filename: source_info.filename.clone(),
                     index,
+                    vector_store_id: Some(source_info.vector_store_id.to_string()),
+                    chunk_id: Some(source_info.chunk_id.to_string()),
+                    chunk_start: Some(source_info.char_start),
+                    chunk_end: Some(source_info.char_end),
+                    score: Some(source_info.score),
                 });
             }
         }
@@ -1174,8 +1191,11 @@ fn format_file_search_completed_event(item_id: &str, output_index: usize) -> Byt
 /// Inject citation annotations into SSE stream chunks.
 ///
 /// This function processes SSE events and adds `FileCitation` annotations
-/// to `response.content_part.done` events based on citation markers found
-/// in the text.
+/// to the `output_text` parts of `response.content_part.done`,
+/// `response.output_item.done` and terminal `response.*` events, based on
+/// citation markers found in the text. The latter two are what the
+/// non-streaming bridge and response persistence read, so annotating them
+/// keeps the citations on the stored response.
 ///
 /// Returns the modified chunk with annotations injected, or the original
 /// chunk if no modifications were needed.
@@ -1202,30 +1222,32 @@ fn inject_citation_annotations(chunk: &[u8], tracker: &CitationTracker) -> Bytes
             if let Ok(mut json) = serde_json::from_str::<Value>(data) {
                 let event_type = json.get("type").and_then(|t| t.as_str()).unwrap_or("");
 
-                // Handle response.content_part.done events
-                if event_type == "response.content_part.done"
-                    && let Some(part) = json.get_mut("part")
-                    && let Some(part_obj) = part.as_object_mut()
-                    && part_obj.get("type").and_then(|t| t.as_str()) == Some("output_text")
-                    && let Some(text) = part_obj.get("text").and_then(|t| t.as_str())
-                {
-                    // Parse citations from the text
-                    let annotations = tracker.parse_citations(text);
-
-                    if !annotations.is_empty() {
-                        // Serialize annotations
-                        let annotations_json =
-                            serde_json::to_value(&annotations).unwrap_or(serde_json::json!([]));
-
-                        // Update the annotations field
-                        part_obj.insert("annotations".to_string(), annotations_json);
-
-                        debug!(
-                            stage = "annotations_injected",
-                            annotation_count = annotations.len(),
-                            "Injected citation annotations into response.content_part.done"
-                        );
-                    }
+                let annotation_count = match event_type {
+                    "response.content_part.done" => json
+                        .get_mut("part")
+                        .map_or(0, |part| annotate_output_text(part, tracker)),
+                    "response.output_item.done" => json
+                        .get_mut("item")
+                        .map_or(0, |item| annotate_message_item(item, tracker)),
+                    "response.completed" | "response.incomplete" => json
+                        .pointer_mut("/response/output")
+                        .and_then(Value::as_array_mut)
+                        .map_or(0, |items| {
+                            items
+                                .iter_mut()
+                                .map(|item| annotate_message_item(item, tracker))
+                                .sum()
+                        }),
+                    _ => 0,
+                };
+
+                if annotation_count > 0 {
+                    debug!(
+                        stage = "annotations_injected",
+                        event_type = event_type,
+                        annotation_count = annotation_count,
+                        "Injected citation annotations"
+                    );
                 }
 
                 // Re-serialize and format as SSE
@@ -1245,6 +1267,44 @@ fn inject_citation_annotations(chunk: &[u8], tracker: &CitationTracker) -> Bytes
     Bytes::from(output)
 }
 
+/// Annotate every `output_text` part of a `message` output item.
+fn annotate_message_item(item: &mut Value, tracker: &CitationTracker) -> usize {
+    if item.get("type").and_then(|t| t.as_str()) != Some("message") {
+        return 0;
+    }
+    item.get_mut("content")
+        .and_then(Value::as_array_mut)
+        .map_or(0, |parts| {
+            parts
+                .iter_mut()
+                .map(|part| annotate_output_text(part, tracker))
+                .sum()
+        })
+}
+
+/// Set an `output_text` part's annotations from the citation markers in its
+/// text. Returns the number of annotations set; parts without markers are
+/// left untouched.
+fn annotate_output_text(part: &mut Value, tracker: &CitationTracker) -> usize {
+    let Some(part_obj) = part.as_object_mut() else {
+        return 0;
+    };
+    if part_obj.get("type").and_then(|t| t.as_str()) != Some("output_text") {
+        return 0;
+    }
+    let Some(text) = part_obj.get("text").and_then(|t| t.as_str()) else {
+        return 0;
+    };
+
+    let annotations = tracker.parse_citations(text);
+    if annotations.is_empty() {
+        return 0;
+    }
+    let annotations_json = serde_json::to_value(&annotations).unwrap_or(serde_json::json!([]));
+    part_obj.insert("annotations".to_string(), annotations_json);
+    annotations.len()
+}
+
 /// Parse a file_search tool call from a JSON value.
 ///
 /// Expected format (from model response):
@@ -1815,7 +1875,7 @@ impl crate::services::server_tools::ServerExecutedTool for FileSearchExecutor {
         let event = self
             .suppressor
             .suppress(event, |name| name == "file_search");
-        if event.is_empty() {
+        if event.is_empty() || !self.context.config.include_annotations {
             return event;
         }
         let Ok(tracker) = self.citation_tracker.lock() else {
@@ -2235,6 +2295,8 @@ mod tests {
                 file_id,
                 chunk_index: 0,
                 content: "This is the content of the chunk.".to_string(),
+                char_start: 0,
+                char_end: 0,
                 score: 0.95,
                 filename: Some("report.pdf".to_string()),
                 metadata: None,
@@ -2275,6 +2337,8 @@ mod tests {
                 file_id,
                 chunk_index: 0,
                 content: "Content without filename.".to_string(),
+                char_start: 0,
+                char_end: 0,
                 score: 0.80,
                 filename: None,
                 metadata: None,
@@ -2303,6 +2367,8 @@ mod tests {
                 file_id,
                 chunk_index: 0,
                 content: "Test content".to_string(),
+                char_start: 0,
+                char_end: 0,
                 score: 0.85,
                 filename: Some("test.pdf".to_string()),
                 metadata: None,
@@ -2334,6 +2400,8 @@ mod tests {
                 file_id,
                 chunk_index: 0,
                 content: "Test content".to_string(),
+                char_start: 0,
+                char_end: 0,
                 score: 0.85,
                 filename: Some("test.pdf".to_string()),
                 metadata: Some(serde_json::json!({"author": "Test Author"})),
@@ -2485,6 +2553,8 @@ mod tests {
                     file_id: file_id_1,
                     chunk_index: 0,
                     content: "Content 1".to_string(),
+                    char_start: 0,
+                    char_end: 0,
                     score: 0.95,
                     filename: Some("report.pdf".to_string()),
                     metadata: None,
@@ -2495,6 +2565,8 @@ mod tests {
                     file_id: file_id_2,
                     chunk_index: 0,
                     content: "Content 2".to_string(),
+                    char_start: 0,
+                    char_end: 0,
                     score: 0.85,
                     filename: None, // No filename, should use file_id
                     metadata: None,
@@ -2535,6 +2607,8 @@ mod tests {
                 file_id,
                 chunk_index: 0,
                 content: "Content".to_string(),
+                char_start: 120,
+                char_end: 480,
                 score: 0.95,
                 filename: Some("report.pdf".to_string()),
                 metadata: None,
@@ -2555,12 +2629,19 @@ mod tests {
             file_id: fid,
             filename,
             index,
+            chunk_start,
+            chunk_end,
+            score,
+            ..
         } = &annotations[0]
         {
             assert_eq!(fid, &file_id.to_string());
             assert_eq!(filename, "report.pdf");
             // Index should be position of "[Source 1]" in the text
             assert_eq!(*index as usize, text.find("[Source 1]").unwrap());
+            assert_eq!(*chunk_start, Some(120));
+            assert_eq!(*chunk_end, Some(480));
+            assert_eq!(*score, Some(0.95));
         } else {
             panic!("Expected FileCitation annotation");
         }
@@ -2580,6 +2661,8 @@ mod tests {
                     file_id: file_id_1,
                     chunk_index: 0,
                     content: "Content 1".to_string(),
+                    char_start: 0,
+                    char_end: 0,
                     score: 0.95,
                     filename: Some("doc1.pdf".to_string()),
                     metadata: None,
@@ -2590,6 +2673,8 @@ mod tests {
                     file_id: file_id_2,
                     chunk_index: 0,
                     content: "Content 2".to_string(),
+                    char_start: 0,
+                    char_end: 0,
                     score: 0.85,
                     filename: Some("doc2.pdf".to_string()),
                     metadata: None,
@@ -2633,6 +2718,8 @@ mod tests {
                 file_id,
                 chunk_index: 0,
                 content: "Content".to_string(),
+                char_start: 0,
+                char_end: 0,
                 score: 0.95,
                 filename: Some("report.pdf".to_string()),
                 metadata: None,
@@ -2664,6 +2751,8 @@ mod tests {
                 file_id,
                 chunk_index: 0,
                 content: "Content".to_string(),
+                char_start: 0,
+                char_end: 0,
                 score: 0.95,
                 filename: Some("report.pdf".to_string()),
                 metadata: None,
@@ -2706,6 +2795,8 @@ mod tests {
                 file_id,
                 chunk_index: 0,
                 content: "Content".to_string(),
+                char_start: 0,
+                char_end: 0,
                 score: 0.95,
                 filename: Some("report.pdf".to_string()),
                 metadata: None,
@@ -2765,6 +2856,8 @@ mod tests {
                 file_id,
                 chunk_index: 0,
                 content: "Content".to_string(),
+                char_start: 0,
+                char_end: 0,
                 score: 0.95,
                 filename: Some("report.pdf".to_string()),
                 metadata: None,
@@ -2809,6 +2902,8 @@ mod tests {
                 file_id: Uuid::new_v4(),
                 chunk_index: 0,
                 content: "Content".to_string(),
+                char_start: 0,
+                char_end: 0,
                 score: 0.95,
                 filename: Some("report.pdf".to_string()),
                 metadata: None,
@@ -2824,6 +2919,62 @@ mod tests {
         assert_eq!(result_str, chunk);
     }
 
+    #[test]
+    fn test_inject_citation_annotations_into_stored_output() {
+        use crate::services::{FileSearchResponse, FileSearchResult};
+
+        let chunk_id = Uuid::new_v4();
+        let mut tracker = CitationTracker::new();
+        tracker.add_from_response(&FileSearchResponse {
+            results: vec![FileSearchResult {
+                chunk_id,
+                vector_store_id: Uuid::new_v4(),
+                file_id: Uuid::new_v4(),
+                chunk_index: 3,
+                content: "Content".to_string(),
+                char_start: 1840,
+                char_end: 2912,
+                score: 0.87,
+                filename: Some("report.pdf".to_string()),
+                metadata: None,
+            }],
+            query: "test".to_string(),
+            vector_stores_searched: 1,
+        });
+
+        let message = serde_json::json!({
+            "type": "message",
+            "role": "assistant",
+            "content": [{"type": "output_text", "text": "Revenue grew [Source 1].", "annotations": []}]
+        });
+        let chunk = format!(
+            "data: {}\n\ndata: {}\n\n",
+            serde_json::json!({"type": "response.output_item.done", "item": message}),
+            serde_json::json!({"type": "response.completed", "response": {"output": [message]}}),
+        );
+
+        let result = inject_citation_annotations(chunk.as_bytes(), &tracker);
+        let events: Vec<Value> = std::str::from_utf8(&result)
+            .unwrap()
+            .split("\n\n")
+            .filter_map(|e| e.strip_prefix("data: "))
+            .map(|e| serde_json::from_str(e).unwrap())
+            .collect();
+        assert_eq!(events.len(), 2);
+
+        for annotation in [
+            &events[0]["item"]["content"][0]["annotations"][0],
+            &events[1]["response"]["output"][0]["content"][0]["annotations"][0],
+        ] {
+            assert_eq!(annotation["type"], "file_citation");
+            assert_eq!(annotation["index"], 13);
+            assert_eq!(annotation["chunk_id"], chunk_id.to_string());
+            assert_eq!(annotation["chunk_start"], 1840);
+            assert_eq!(annotation["chunk_end"], 2912);
+            assert_eq!(annotation["score"], 0.87);
+        }
+    }
+
     // =========================================================================
     // FileSearchToolArguments Schema Tests
     // =========================================================================
@@ -3778,6 +3929,8 @@ mod tests {
                     file_id: Uuid::new_v4(),
                     chunk_index: 0,
                     content: "A".repeat(1000),
+                    char_start: 0,
+                    char_end: 0,
                     score: 0.95,
                     filename: Some("file1.txt".to_string()),
                     metadata: None,
@@ -3788,6 +3941,8 @@ mod tests {
                     file_id: Uuid::new_v4(),
                     chunk_index: 0,
                     content: "B".repeat(1000),
+                    char_start: 0,
+                    char_end: 0,
                     score: 0.85,
                     filename: Some("file2.txt".to_string()),
                     metadata: None,
@@ -3816,6 +3971,8 @@ mod tests {
                     file_id: Uuid::new_v4(),
                     chunk_index: 0,
                     content: "A".repeat(500),
+                    char_start: 0,
+                    char_end: 0,
                     score: 0.95,
                     filename: Some("file1.txt".to_string()),
                     metadata: None,
@@ -3826,6 +3983,8 @@ mod tests {
                     file_id: Uuid::new_v4(),
                     chunk_index: 0,
                     content: "B".repeat(500),
+                    char_start: 0,
+                    char_end: 0,
                     score: 0.85,
                     filename: Some("file2.txt".to_string()),
                     metadata: None,
@@ -3836,6 +3995,8 @@ mod tests {
                     file_id: Uuid::new_v4(),
                     chunk_index: 0,
                     content: "C".repeat(500),
+                    char_start: 0,
+                    char_end: 0,
                     score: 0.75,
                     filename: Some("file3.txt".to_string()),
                     metadata: None,
@@ -3867,6 +4028,8 @@ mod tests {
                 file_id: Uuid::new_v4(),
                 chunk_index: 0,
                 content: "A".repeat(10000),
+                char_start: 0,
+                char_end: 0,
                 score: 0.95,
                 filename: Some("huge_file.txt".to_string()),
                 metadata: None,
@@ -3896,6 +4059,8 @@ mod tests {
                     file_id: Uuid::new_v4(),
                     chunk_index: 0,
                     content: "A".repeat(1000),
+                    char_start: 0,
+                    char_end: 0,
                     score: 0.95,
                     filename: Some("file1.txt".to_string()),
                     metadata: None,
@@ -3906,6 +4071,8 @@ mod tests {
                     file_id: Uuid::new_v4(),
                     chunk_index: 0,
                     content: "B".repeat(1000),
+                    char_start: 0,
+                    char_end: 0,
                     score: 0.85,
                     filename: Some("file2.txt".to_string()),
                     metadata: None,
@@ -3934,6 +4101,8 @@ mod tests {
                     file_id: Uuid::new_v4(),
                     chunk_index: 0,
                     content: "Short content".to_string(),
+                    char_start: 0,
+                    char_end: 0,
                     score: 0.95,
                     filename: Some("small.txt".to_string()),
                     metadata: None,
@@ -3944,6 +4113,8 @@ mod tests {
                     file_id: Uuid::new_v4(),
                     chunk_index: 0,
                     content: "X".repeat(5000),
+                    char_start: 0,
+                    char_end: 0,
                     score: 0.85,
                     filename: Some("large.txt".to_string()),
                     metadata: None,
@@ -0,0 +1,314 @@
+//! Fine-tuning jobs proxied to providers.
+//!
+//! `/v1/fine_tuning/jobs` starts jobs on the provider a model routes to and
+//! records which organization and project own them in `fine_tuning_jobs`.
+//! Jobs are refreshed from the provider whenever a client reads one, and by
+//! the `fine_tuning_sync` job until they finish.
+//!
+//! A finished job is completed exactly once, by whichever node marks it
+//! first: its trained tokens are recorded as usage, priced per
+//! `[features.fine_tuning.training_prices]`. A succeeded job's model is also
+//! added to the model catalog, with its base model's metadata, and to the
+//! owning organization's and project's model allow lists so it can be used
+//! straight away.
+
+use crate::{
+    AppState,
+    api_types::FineTuningJob,
+    cache::CacheKeys,
+    config::ProviderConfig,
+    db::{DbError, DbPool},
+    models::{
+        CatalogEntryMetadata, CreateModelCatalogEntry, FineTuningJobRecord, FineTuningJobStatus,
+        UsageLogEntry,
+    },
+    pricing::{CostPricingSource, dollars_to_microcents},
+    providers::ProviderError,
+    services::provider_files,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum FineTuningError {
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+    #[error(transparent)]
+    Db(#[from] DbError),
+}
+
+/// Render a job for the client: the gateway's job ID and the file IDs the
+/// client gave replace the provider's.
+pub fn to_client_job(record: &FineTuningJobRecord, job: Option<FineTuningJob>) -> FineTuningJob {
+    let mut job = job.unwrap_or_else(|| FineTuningJob {
+        id: String::new(),
+        object: "fine_tuning.job".to_string(),
+        model: record.model.clone(),
+        status: record.status.clone(),
+        fine_tuned_model: record.fine_tuned_model.clone(),
+        trained_tokens: record.trained_tokens,
+        training_file: String::new(),
+        validation_file: None,
+        created_at: record.created_at.timestamp(),
+        finished_at: None,
+        error: None,
+        extra: serde_json::Map::new(),
+    });
+    job.id = record.public_id();
+    job.training_file = record.training_file.clone();
+    job.validation_file = record.validation_file.clone();
+    job
+}
+
+/// Fetch a job from its provider and store its latest status, completing it
+/// if it has finished.
+pub async fn sync_job(
+    state: &AppState,
+    db: &DbPool,
+    record: &FineTuningJobRecord,
+) -> Result<(FineTuningJobRecord, FineTuningJob), FineTuningError> {
+    let (config, provider) = provider_files::configured_provider(state, &record.provider)?;
+    let job = provider
+        .get_fine_tuning_job(&state.http_client, &record.provider_job_id)
+        .await?;
+    let record = store_status(state, db, record, &config, &job).await?;
+    Ok((record, job))
+}
+
+/// Store the status a provider reported for a job, completing it if it has
+/// finished.
+pub async fn store_status(
+    state: &AppState,
+    db: &DbPool,
+    record: &FineTuningJobRecord,
+    config: &ProviderConfig,
+    job: &FineTuningJob,
+) -> Result<FineTuningJobRecord, FineTuningError> {
+    let status = FineTuningJobStatus {
+        status: job.status.clone(),
+        fine_tuned_model: job.fine_tuned_model.clone(),
+        trained_tokens: job.trained_tokens,
+    };
+    let record = if status.status != record.status
+        || status.fine_tuned_model != record.fine_tuned_model
+        || status.trained_tokens != record.trained_tokens
+    {
+        db.fine_tuning_jobs()
+            .update_status(record.id, &status)
+            .await?
+    } else {
+        record.clone()
+    };
+
+    if job.is_finished() && db.fine_tuning_jobs().mark_completed(record.id).await? {
+        complete_job(state, db, &record, config).await;
+    }
+    Ok(record)
+}
+
+/// Record a finished job's usage and register its model. Failures are
+/// logged rather than returned: the job is already marked completed.
+async fn complete_job(
+    state: &AppState,
+    db: &DbPool,
+    record: &FineTuningJobRecord,
+    config: &ProviderConfig,
+) {
+    if let Some(tokens) = record.trained_tokens.filter(|t| *t > 0)
+        && let Err(e) = db.usage().log(training_usage(state, record, tokens)).await
+    {
+        tracing::warn!(
+            error = %e,
+            job_id = %record.public_id(),
+            "Failed to record fine-tuning usage"
+        );
+    }
+
+    let Some(model) = record
+        .fine_tuned_model
+        .as_deref()
+        .filter(|_| record.status == "succeeded")
+    else {
+        return;
+    };
+    if let Err(e) = register_model(state, db, record, config, model).await {
+        tracing::warn!(
+            error = %e,
+            job_id = %record.public_id(),
+            model,
+            "Failed to add fine-tuned model to the catalog"
+        );
+    }
+    if let Err(e) = allow_model(state, db, record, model).await {
+        tracing::warn!(
+            error = %e,
+            job_id = %record.public_id(),
+            model,
+            "Failed to add fine-tuned model to model access policies"
+        );
+    }
+    tracing::info!(
+        job_id = %record.public_id(),
+        provider = %record.provider,
+        model,
+        "Fine-tuning job succeeded"
+    );
+}
+
+fn training_usage(state: &AppState, record: &FineTuningJobRecord, tokens: i64) -> UsageLogEntry {
+    let cost_microcents = state
+        .config
+        .features
+        .fine_tuning
+        .training_price(&record.provider, &record.model)
+        .map(|price| dollars_to_microcents(price * tokens as f64 / 1_000_000.0));
+    UsageLogEntry {
+        // The job ID keeps the record unique if it is ever written twice
+        request_id: record.public_id(),
+        api_key_id: record.api_key_id,
+        user_id: record.user_id,
+        org_id: Some(record.org_id),
+        project_id: record.project_id,
+        team_id: record.team_id,
+        service_account_id: record.service_account_id,
+        model: record.model.clone(),
+        provider: record.provider.clone(),
+        http_referer: None,
+        input_tokens: i32::try_from(tokens).unwrap_or(i32::MAX),
+        output_tokens: 0,
+        cost_microcents,
+        request_at: chrono::Utc::now(),
+        streamed: false,
+        cached_tokens: 0,
+        reasoning_tokens: 0,
+        finish_reason: None,
+        latency_ms: None,
+        cancelled: record.status == "cancelled",
+        status_code: None,
+        pricing_source: if cost_microcents.is_some() {
+            CostPricingSource::PricingConfig
+        } else {
+            CostPricingSource::None
+        },
+        image_count: None,
+        audio_seconds: None,
+        character_count: None,
+        provider_source: Some("static".to_string()),
+        record_type: "model".to_string(),
+        tool_name: None,
+        tool_query: None,
+        tool_url: None,
+        tool_bytes_fetched: None,
+        tool_results_count: None,
+        tool_runtime_seconds: None,
+        tool_exit_code: None,
+        jwt_subject: None,
+        error_code: None,
+        structured_output_repairs: None,
+        context_original_tokens: None,
+        context_compressed_tokens: None,
+        degraded_from_model: None,
+        degradation_reason: None,
+        ttft_ms: None,
+        inter_token_latency_ms: None,
+        output_tokens_per_second: None,
+        idempotency_key: None,
+        provider_attempts: None,
+        suspected_duplicate_attempts: None,
+        tags: None,
+    }
+}
+
+/// Add a fine-tuned model to the catalog with its base model's metadata.
+/// Pricing isn't copied, as fine-tuned models are priced differently.
+async fn register_model(
+    state: &AppState,
+    db: &DbPool,
+    record: &FineTuningJobRecord,
+    config: &ProviderConfig,
+    model: &str,
+) -> Result<(), DbError> {
+    let Some(catalog_provider) = crate::catalog::resolve_catalog_provider_id(
+        config.provider_type_name(),
+        config.base_url(),
+        config.catalog_provider(),
+    ) else {
+        return Ok(());
+    };
+    let metadata = state
+        .model_catalog
+        .lookup(&catalog_provider, &record.model)
+        .map(|base| CatalogEntryMetadata {
+            family: base.family,
+            capabilities: base.capabilities,
+            modalities: base.modalities,
+            limits: base.limits,
+            pricing: None,
+            knowledge_cutoff: base.knowledge_cutoff,
+            release_date: None,
+            deprecation_date: None,
+            sunset_date: None,
+            open_weights: false,
+        })
+        .unwrap_or_default();
+
+    match db
+        .model_catalog_entries()
+        .create(CreateModelCatalogEntry {
+            provider: catalog_provider,
+            model: model.to_string(),
+            metadata,
+        })
+        .await
+    {
+        // An admin already described the model
+        Ok(_) | Err(DbError::Conflict(_)) => {}
+        Err(e) => return Err(e),
+    }
+    state.model_catalog.load_custom_entries(db).await
+}
+
+/// Add a fine-tuned model to the allow lists of the job's organization and
+/// project, where they have one.
+async fn allow_model(
+    state: &AppState,
+    db: &DbPool,
+    record: &FineTuningJobRecord,
+    model: &str,
+) -> Result<(), DbError> {
+    if let Some(mut policy) = db
+        .organizations()
+        .get_by_id(record.org_id)
+        .await?
+        .and_then(|org| org.model_access)
+        && policy.allow_model(model)
+    {
+        db.organizations()
+            .set_model_access(record.org_id, Some(&policy))
+            .await?;
+        if let Some(cache) = &state.cache {
+            let _ = cache
+                .delete(&CacheKeys::org_model_access(record.org_id))
+                .await;
+        }
+    }
+
+    let Some(project_id) = record.project_id else {
+        return Ok(());
+    };
+    if let Some(mut policy) = db
+        .projects()
+        .get_by_id(project_id)
+        .await?
+        .and_then(|project| project.model_access)
+        && policy.allow_model(model)
+    {
+        db.projects()
+            .set_model_access(project_id, Some(&policy))
+            .await?;
+        if let Some(cache) = &state.cache {
+            let _ = cache
+                .delete(&CacheKeys::project_model_access(project_id))
+                .await;
+        }
+    }
+    Ok(())
+}
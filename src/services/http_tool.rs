@@ -0,0 +1,808 @@
+//! Gateway-executed HTTP tools for the Responses API.
+//!
+//! Covers the built-in `web_fetch` tool (backed by `[features.web_fetch]`),
+//! operator-defined `[[features.server_tools.http_tools]]`, and tools an
+//! organization registered through the admin API. Unlike `web_search` or
+//! `file_search` there's no hosted OpenAI tool type for these: a request
+//! opts in by declaring a function tool with the tool's name,
+//! [`preprocess_http_tools`] and [`resolve_org_http_tools`] swap in the
+//! stored definition, and [`HttpToolExecutor`] runs the calls instead of
+//! returning them to the client. Every call is bounded by a per-tool timeout
+//! and recorded in the audit log.
+
+use std::{
+    collections::HashSet,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use bytes::Bytes;
+use futures_util::StreamExt;
+use serde_json::Value;
+use tracing::{debug, error, info, warn};
+
+use crate::{
+    AppState,
+    api_types::responses::{
+        CreateResponsesPayload, EasyInputMessage, EasyInputMessageContent, EasyInputMessageRole,
+        FunctionCallOutput, FunctionCallOutputType, FunctionTool, ResponsesInput,
+        ResponsesInputItem, ResponsesToolDefinition,
+    },
+    config::{FeaturesConfig, HttpToolConfig, WebFetchConfig},
+    db::{DbPool, DbResult},
+    models::{AuditActorType, CreateAuditLog},
+    routes::api::tools::execute_web_fetch,
+    services::{
+        responses_pipeline::PipelinePrincipal,
+        server_tools::{
+            DetectedToolCall, FunctionCallSuppressor, ServerExecutedTool, ToolCallResult,
+            ToolContext, ToolError, ToolExecutionHandle, invalid_arguments_text,
+        },
+    },
+    validation::url::{UrlValidationOptions, validate_base_url_opts},
+};
+
+/// Name of the built-in fetch tool.
+pub const WEB_FETCH_FUNCTION_NAME: &str = "web_fetch";
+
+/// Routing name shared by every call this executor handles.
+const EXECUTOR_NAME: &str = "http_tool";
+
+fn web_fetch_definition() -> Value {
+    serde_json::json!({
+        "type": "function",
+        "name": WEB_FETCH_FUNCTION_NAME,
+        "description": "Fetch a web page or document by URL and return its text content. HTML is converted to plain text. Use this to read a specific page, such as one returned by a search or given by the user.",
+        "parameters": {
+            "type": "object",
+            "properties": {
+                "url": {
+                    "type": "string",
+                    "description": "Absolute http(s) URL to fetch"
+                }
+            },
+            "required": ["url"],
+            "additionalProperties": false
+        },
+        "strict": false,
+    })
+}
+
+fn http_tool_definition(tool: &HttpToolConfig) -> Value {
+    serde_json::json!({
+        "type": "function",
+        "name": tool.name,
+        "description": tool.description,
+        "parameters": tool
+            .parameters
+            .clone()
+            .unwrap_or_else(|| serde_json::json!({ "type": "object", "properties": {} })),
+        "strict": false,
+    })
+}
+
+fn web_fetch_config(features: &FeaturesConfig) -> Option<&WebFetchConfig> {
+    features.web_fetch.as_ref().filter(|c| c.enabled)
+}
+
+/// Definition for a gateway HTTP tool, or `None` if `name` isn't one.
+fn gateway_definition(features: &FeaturesConfig, name: &str) -> Option<Value> {
+    if name == WEB_FETCH_FUNCTION_NAME {
+        return web_fetch_config(features).map(|_| web_fetch_definition());
+    }
+    features
+        .server_tools
+        .http_tool(name)
+        .map(http_tool_definition)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Payload Preprocessing
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Replace function tools named after a gateway HTTP tool with the
+/// configured definition, so the model sees the schema the endpoint
+/// actually accepts regardless of what the client sent.
+pub fn preprocess_http_tools(payload: &mut CreateResponsesPayload, features: &FeaturesConfig) {
+    replace_definitions(payload, |name| gateway_definition(features, name));
+}
+
+/// Swap each function tool `definition` knows about for the gateway's
+/// definition.
+fn replace_definitions(
+    payload: &mut CreateResponsesPayload,
+    definition: impl Fn(&str) -> Option<Value>,
+) {
+    let Some(tools) = payload.tools.as_mut() else {
+        return;
+    };
+
+    for tool in tools.iter_mut() {
+        let ResponsesToolDefinition::Function(function) = tool else {
+            continue;
+        };
+        let Some(definition) = definition(&function.name) else {
+            continue;
+        };
+        let name = function.name.clone();
+        *tool = ResponsesToolDefinition::Function(
+            FunctionTool::from_json(definition).expect("http tool definition is well-formed"),
+        );
+        debug!(
+            stage = "tool_preprocessed",
+            tool = %name,
+            "Preprocessed HTTP tool to gateway definition"
+        );
+    }
+}
+
+/// Resolve the HTTP tools `org_id` registered through the admin API that
+/// `payload` declares, and swap in their stored definitions. Configured
+/// tools take precedence over a registered tool with the same name.
+///
+/// The returned tools are handed to [`HttpToolExecutor::new`]. The
+/// database is only consulted when the request declares a function tool
+/// that isn't a configured one.
+pub async fn resolve_org_http_tools(
+    state: &AppState,
+    payload: &mut CreateResponsesPayload,
+    org_id: Option<uuid::Uuid>,
+) -> DbResult<Vec<HttpToolConfig>> {
+    let features = &state.config.features;
+    let candidates: HashSet<&str> = payload
+        .tools
+        .iter()
+        .flatten()
+        .filter_map(|t| match t {
+            ResponsesToolDefinition::Function(f)
+                if gateway_definition(features, &f.name).is_none() =>
+            {
+                Some(f.name.as_str())
+            }
+            _ => None,
+        })
+        .collect();
+    let (Some(org_id), Some(services)) = (org_id, state.services.as_ref()) else {
+        return Ok(Vec::new());
+    };
+    if candidates.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let tools: Vec<HttpToolConfig> = services
+        .http_tools
+        .list_by_org(org_id)
+        .await?
+        .iter()
+        .filter(|t| candidates.contains(t.name.as_str()))
+        .map(|t| t.to_config())
+        .collect();
+    if !tools.is_empty() {
+        replace_definitions(payload, |name| {
+            tools
+                .iter()
+                .find(|t| t.name == name)
+                .map(http_tool_definition)
+        });
+    }
+    Ok(tools)
+}
+
+/// Whether `payload` declares a configured gateway HTTP tool.
+pub fn declares_gateway_tools(payload: &CreateResponsesPayload, features: &FeaturesConfig) -> bool {
+    !declared_tool_names(payload, features).is_empty()
+}
+
+/// Names of the gateway HTTP tools declared in `payload`.
+fn declared_tool_names(payload: &CreateResponsesPayload, features: &FeaturesConfig) -> Vec<String> {
+    payload
+        .tools
+        .iter()
+        .flatten()
+        .filter_map(|t| match t {
+            ResponsesToolDefinition::Function(f)
+                if gateway_definition(features, &f.name).is_some() =>
+            {
+                Some(f.name.clone())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Detection
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Parse a `function_call` item for one of `names`.
+fn parse_http_tool_call(value: &Value, names: &HashSet<String>) -> Option<DetectedToolCall> {
+    let obj = value.as_object()?;
+    if obj.get("type")?.as_str()? != "function_call" {
+        return None;
+    }
+    let name = obj.get("name")?.as_str()?;
+    if !names.contains(name) {
+        return None;
+    }
+    let id = obj
+        .get("call_id")
+        .or_else(|| obj.get("id"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("unknown")
+        .to_string();
+
+    let arguments = obj.get("arguments").and_then(|a| a.as_str()).unwrap_or("");
+    match serde_json::from_str::<Value>(arguments) {
+        Ok(args) if args.is_object() => Some(DetectedToolCall::new(
+            EXECUTOR_NAME,
+            id,
+            serde_json::json!({ "name": name, "arguments": args }),
+        )),
+        Ok(_) => Some(invalid_call(name, id, "`arguments` must be a JSON object")),
+        Err(e) => Some(invalid_call(
+            name,
+            id,
+            &format!("could not parse `arguments`: {e}"),
+        )),
+    }
+}
+
+fn invalid_call(name: &str, id: String, error: &str) -> DetectedToolCall {
+    let mut call = DetectedToolCall::invalid(EXECUTOR_NAME, id, error);
+    // Keep the function name so the error fed back names the right tool
+    call.arguments = serde_json::json!({ "name": name });
+    call
+}
+
+/// Detect HTTP tool calls in an SSE chunk. Only the canonical
+/// `response.output_item.done` event (and bare `function_call` items) are
+/// inspected, so each call executes once.
+fn detect_http_tools_in_chunk(chunk: &[u8], names: &HashSet<String>) -> Vec<DetectedToolCall> {
+    let Ok(chunk_str) = std::str::from_utf8(chunk) else {
+        return Vec::new();
+    };
+
+    let mut found = Vec::new();
+    for line in chunk_str.lines() {
+        let Some(data) = line.strip_prefix("data:").map(str::trim) else {
+            continue;
+        };
+        if data == "[DONE]" {
+            continue;
+        }
+        let Ok(json) = serde_json::from_str::<Value>(data) else {
+            continue;
+        };
+        if json.get("type").and_then(|t| t.as_str()) == Some("response.output_item.done")
+            && let Some(item) = json.get("item")
+            && let Some(call) = parse_http_tool_call(item, names)
+        {
+            found.push(call);
+        } else if let Some(call) = parse_http_tool_call(&json, names) {
+            found.push(call);
+        }
+    }
+    found
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Execution
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Where and on whose behalf tool calls are audited.
+#[derive(Clone)]
+pub struct HttpToolAudit {
+    pub db: Arc<DbPool>,
+    pub principal: PipelinePrincipal,
+    pub response_id: Option<String>,
+}
+
+/// Outcome of one call, before it's rendered for the model.
+struct CallOutcome {
+    output: String,
+    status_code: Option<u16>,
+    error: Option<String>,
+}
+
+impl CallOutcome {
+    fn failed(output: String, status_code: Option<u16>, error: impl Into<String>) -> Self {
+        Self {
+            output,
+            status_code,
+            error: Some(error.into()),
+        }
+    }
+}
+
+/// `ServerExecutedTool` implementation for `web_fetch`, operator-defined
+/// HTTP tools, and tools registered by the request's organization.
+pub struct HttpToolExecutor {
+    http_client: reqwest::Client,
+    web_fetch: Option<WebFetchConfig>,
+    url_options: UrlValidationOptions,
+    http_tools: Vec<HttpToolConfig>,
+    /// Tools from [`resolve_org_http_tools`]. Their URLs come from
+    /// organization admins rather than the operator, so every call is held
+    /// to the same SSRF rules as `web_fetch`.
+    org_tools: Vec<HttpToolConfig>,
+    /// Function names this request declared.
+    names: HashSet<String>,
+    audit: Option<HttpToolAudit>,
+    /// Hides the function-call plumbing: the client never has to act on
+    /// these calls.
+    suppressor: FunctionCallSuppressor,
+}
+
+impl HttpToolExecutor {
+    pub fn new(
+        http_client: reqwest::Client,
+        features: &FeaturesConfig,
+        url_options: UrlValidationOptions,
+        payload: &CreateResponsesPayload,
+        org_tools: Vec<HttpToolConfig>,
+        audit: Option<HttpToolAudit>,
+    ) -> Self {
+        let mut names: HashSet<String> =
+            declared_tool_names(payload, features).into_iter().collect();
+        names.extend(org_tools.iter().map(|t| t.name.clone()));
+        Self {
+            http_client,
+            web_fetch: web_fetch_config(features).cloned(),
+            url_options,
+            http_tools: features
+                .server_tools
+                .http_tools
+                .iter()
+                .filter(|t| names.contains(&t.name))
+                .cloned()
+                .collect(),
+            org_tools,
+            names,
+            audit,
+            suppressor: FunctionCallSuppressor::new(),
+        }
+    }
+
+    /// Whether the request declared any gateway HTTP tool.
+    pub fn has_tools(&self) -> bool {
+        !self.names.is_empty()
+    }
+
+    async fn run_web_fetch(&self, config: &WebFetchConfig, arguments: &Value) -> CallOutcome {
+        let Some(url) = arguments.get("url").and_then(|u| u.as_str()) else {
+            let text = invalid_arguments_text(WEB_FETCH_FUNCTION_NAME, "missing string `url`");
+            return CallOutcome::failed(text, None, "missing url");
+        };
+        match execute_web_fetch(config, self.url_options, url, config.max_response_bytes).await {
+            Ok(page) => CallOutcome {
+                output: page.content,
+                status_code: Some(200),
+                error: None,
+            },
+            Err(e) => {
+                CallOutcome::failed(format!("web_fetch failed for {url}: {e}"), None, e.code())
+            }
+        }
+    }
+
+    /// Call an organization's tool through a client pinned to the addresses
+    /// its URL validated against, with redirects disabled.
+    async fn run_org_tool(&self, tool: &HttpToolConfig, arguments: &Value) -> CallOutcome {
+        let validated = match validate_base_url_opts(&tool.url, self.url_options) {
+            Ok(validated) => validated,
+            Err(e) => {
+                warn!(tool = %tool.name, url = %tool.url, error = %e, "HTTP tool URL blocked");
+                return CallOutcome::failed(
+                    format!("Tool `{}` is not reachable from the gateway", tool.name),
+                    None,
+                    "blocked url",
+                );
+            }
+        };
+
+        // redirect/resolve are unavailable in WASM reqwest.
+        let builder = reqwest::Client::builder();
+        #[cfg(not(target_arch = "wasm32"))]
+        let mut builder = builder.redirect(reqwest::redirect::Policy::none());
+        #[cfg(not(target_arch = "wasm32"))]
+        for addr in &validated.addrs {
+            builder = builder.resolve(&validated.host, *addr);
+        }
+        match builder.build() {
+            Ok(client) => self.send(&client, tool, arguments).await,
+            Err(e) => {
+                error!(error = %e, "Failed to build pinned HTTP client");
+                CallOutcome::failed(
+                    format!("Tool `{}` request failed", tool.name),
+                    None,
+                    "client",
+                )
+            }
+        }
+    }
+
+    async fn run_http_tool(&self, tool: &HttpToolConfig, arguments: &Value) -> CallOutcome {
+        self.send(&self.http_client, tool, arguments).await
+    }
+
+    async fn send(
+        &self,
+        client: &reqwest::Client,
+        tool: &HttpToolConfig,
+        arguments: &Value,
+    ) -> CallOutcome {
+        let mut request = client
+            .post(&tool.url)
+            .timeout(Duration::from_secs(tool.timeout_secs))
+            .json(arguments);
+        for (name, value) in &tool.headers {
+            request = request.header(name, value);
+        }
+
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(e) => {
+                let reason = if e.is_timeout() {
+                    format!("timed out after {}s", tool.timeout_secs)
+                } else {
+                    "request failed".to_string()
+                };
+                warn!(tool = %tool.name, error = %e, "HTTP tool request failed");
+                return CallOutcome::failed(format!("Tool `{}` {reason}", tool.name), None, reason);
+            }
+        };
+
+        let status = response.status();
+        let mut body = Vec::new();
+        let mut stream = response.bytes_stream();
+        while let Some(chunk) = stream.next().await {
+            match chunk {
+                Ok(chunk) => {
+                    body.extend_from_slice(&chunk);
+                    if body.len() >= tool.max_response_bytes {
+                        body.truncate(tool.max_response_bytes);
+                        break;
+                    }
+                }
+                Err(e) => {
+                    warn!(tool = %tool.name, error = %e, "Failed to read HTTP tool response");
+                    let reason = if e.is_timeout() {
+                        format!("timed out after {}s", tool.timeout_secs)
+                    } else {
+                        "response could not be read".to_string()
+                    };
+                    return CallOutcome::failed(
+                        format!("Tool `{}` {reason}", tool.name),
+                        Some(status.as_u16()),
+                        reason,
+                    );
+                }
+            }
+        }
+        let text = String::from_utf8_lossy(&body).into_owned();
+
+        if status.is_success() {
+            CallOutcome {
+                output: text,
+                status_code: Some(status.as_u16()),
+                error: None,
+            }
+        } else {
+            CallOutcome::failed(
+                format!(
+                    "Tool `{}` returned status {}: {text}",
+                    tool.name,
+                    status.as_u16()
+                ),
+                Some(status.as_u16()),
+                format!("status {}", status.as_u16()),
+            )
+        }
+    }
+
+    async fn audit(&self, name: &str, call_id: &str, outcome: &CallOutcome, duration: Duration) {
+        let Some(audit) = &self.audit else {
+            return;
+        };
+        let principal = &audit.principal;
+        let (actor_type, actor_id) = if let Some(id) = principal.api_key_id {
+            (AuditActorType::ApiKey, Some(id))
+        } else if let Some(id) = principal.user_id {
+            (AuditActorType::User, Some(id))
+        } else {
+            (AuditActorType::System, None)
+        };
+        let url = if name == WEB_FETCH_FUNCTION_NAME {
+            None
+        } else {
+            self.http_tools
+                .iter()
+                .chain(&self.org_tools)
+                .find(|t| t.name == name)
+                .map(|t| t.url.clone())
+        };
+
+        let result = audit
+            .db
+            .audit_logs()
+            .create(CreateAuditLog {
+                actor_type,
+                actor_id,
+                action: "tool.execute".to_string(),
+                resource_type: "tool_call".to_string(),
+                resource_id: uuid::Uuid::new_v4(),
+                org_id: principal.org_id,
+                project_id: principal.project_id,
+                details: serde_json::json!({
+                    "tool": name,
+                    "call_id": call_id,
+                    "url": url,
+                    "response_id": audit.response_id,
+                    "status_code": outcome.status_code,
+                    "error": outcome.error,
+                    "duration_ms": duration.as_millis() as u64,
+                }),
+                ip_address: None,
+                user_agent: None,
+            })
+            .await;
+        if let Err(e) = result {
+            warn!(error = %e, tool = %name, "Failed to write tool.execute audit event");
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ServerExecutedTool for HttpToolExecutor {
+    fn name(&self) -> &'static str {
+        EXECUTOR_NAME
+    }
+
+    fn transform_event(&self, event: Bytes) -> Bytes {
+        self.suppressor
+            .suppress(event, |name| self.names.contains(name))
+    }
+
+    fn is_enabled_for(&self, _payload: &CreateResponsesPayload) -> bool {
+        self.has_tools()
+    }
+
+    fn detect(&self, event: &[u8], _ctx: &ToolContext) -> Vec<DetectedToolCall> {
+        detect_http_tools_in_chunk(event, &self.names)
+    }
+
+    async fn execute(
+        &self,
+        call: DetectedToolCall,
+        _ctx: &ToolContext,
+    ) -> Result<ToolExecutionHandle, ToolError> {
+        let name = call
+            .arguments
+            .get("name")
+            .and_then(|n| n.as_str())
+            .unwrap_or_default()
+            .to_string();
+
+        let start = Instant::now();
+        let outcome = if let Some(error) = &call.invalid {
+            CallOutcome::failed(invalid_arguments_text(&name, error), None, error.clone())
+        } else {
+            let arguments = &call.arguments["arguments"];
+            if name == WEB_FETCH_FUNCTION_NAME
+                && let Some(config) = &self.web_fetch
+            {
+                self.run_web_fetch(config, arguments).await
+            } else if let Some(tool) = self.http_tools.iter().find(|t| t.name == name) {
+                self.run_http_tool(tool, arguments).await
+            } else if let Some(tool) = self.org_tools.iter().find(|t| t.name == name) {
+                self.run_org_tool(tool, arguments).await
+            } else {
+                return Err(ToolError::InvalidCall(format!(
+                    "unknown HTTP tool `{name}`"
+                )));
+            }
+        };
+        let duration = start.elapsed();
+
+        info!(
+            stage = "http_tool_executed",
+            tool = %name,
+            call_id = %call.call_id,
+            status_code = ?outcome.status_code,
+            error = ?outcome.error,
+            duration_ms = duration.as_millis() as u64,
+            "Executed HTTP tool"
+        );
+        self.audit(&name, &call.call_id, &outcome, duration).await;
+
+        let result = ToolCallResult {
+            call_id: call.call_id.clone(),
+            continuation_items: vec![ResponsesInputItem::FunctionCallOutput(FunctionCallOutput {
+                type_: FunctionCallOutputType::FunctionCallOutput,
+                id: Some(call.call_id.clone()),
+                call_id: call.call_id,
+                output: outcome.output,
+                status: None,
+            })],
+            stop_loop: false,
+        };
+
+        Ok(ToolExecutionHandle {
+            events: Box::pin(futures_util::stream::empty()),
+            result: Box::pin(async move { Ok(result) }),
+        })
+    }
+
+    fn apply_to_continuation(
+        &self,
+        payload: &mut CreateResponsesPayload,
+        results: &[ToolCallResult],
+        is_final_iteration: bool,
+    ) {
+        let function_outputs: Vec<ResponsesInputItem> = results
+            .iter()
+            .flat_map(|r| r.continuation_items.clone())
+            .collect();
+
+        if !function_outputs.is_empty() {
+            match payload.input {
+                Some(ResponsesInput::Items(ref mut items)) => items.extend(function_outputs),
+                Some(ResponsesInput::Text(ref text)) => {
+                    let mut items = vec![ResponsesInputItem::EasyMessage(EasyInputMessage {
+                        type_: None,
+                        role: EasyInputMessageRole::User,
+                        content: EasyInputMessageContent::Text(text.clone()),
+                    })];
+                    items.extend(function_outputs);
+                    payload.input = Some(ResponsesInput::Items(items));
+                }
+                None => payload.input = Some(ResponsesInput::Items(function_outputs)),
+            }
+        }
+
+        // Strip our tool definitions on the final iteration.
+        if is_final_iteration && let Some(ref mut tools) = payload.tools {
+            tools.retain(|t| {
+                !matches!(t, ResponsesToolDefinition::Function(f) if self.names.contains(&f.name))
+            });
+            if tools.is_empty() {
+                payload.tools = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn features() -> FeaturesConfig {
+        let mut features = FeaturesConfig::default();
+        features.web_fetch = Some(WebFetchConfig::default());
+        features.server_tools.http_tools = vec![
+            serde_json::from_value(serde_json::json!({
+                "name": "lookup_order",
+                "description": "Look up an order",
+                "url": "http://orders.internal/lookup",
+                "parameters": {
+                    "type": "object",
+                    "properties": { "order_id": { "type": "string" } }
+                }
+            }))
+            .unwrap(),
+        ];
+        features
+    }
+
+    fn payload(tools: Value) -> CreateResponsesPayload {
+        serde_json::from_value(serde_json::json!({
+            "model": "test",
+            "input": "hi",
+            "tools": tools,
+        }))
+        .unwrap()
+    }
+
+    fn names(list: &[&str]) -> HashSet<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn preprocess_replaces_declared_definitions() {
+        let features = features();
+        let mut payload = payload(serde_json::json!([
+            { "type": "function", "name": "lookup_order" },
+            { "type": "function", "name": "web_fetch", "description": "client text" },
+            { "type": "function", "name": "client_tool", "parameters": { "type": "object" } },
+        ]));
+        preprocess_http_tools(&mut payload, &features);
+
+        let tools = payload.tools.as_ref().unwrap();
+        let ResponsesToolDefinition::Function(order) = &tools[0] else {
+            panic!("expected function tool");
+        };
+        assert_eq!(order.description.as_deref(), Some("Look up an order"));
+        assert!(order.parameters.as_ref().unwrap()["properties"]["order_id"].is_object());
+        let ResponsesToolDefinition::Function(fetch) = &tools[1] else {
+            panic!("expected function tool");
+        };
+        assert_ne!(fetch.description.as_deref(), Some("client text"));
+        assert_eq!(fetch.parameters.as_ref().unwrap()["required"][0], "url");
+        let ResponsesToolDefinition::Function(client) = &tools[2] else {
+            panic!("expected function tool");
+        };
+        assert!(client.description.is_none());
+    }
+
+    #[test]
+    fn web_fetch_needs_feature_enabled() {
+        let mut features = features();
+        features.web_fetch = None;
+        let payload = payload(serde_json::json!([
+            { "type": "function", "name": "web_fetch" },
+            { "type": "function", "name": "lookup_order" },
+        ]));
+        assert_eq!(
+            declared_tool_names(&payload, &features),
+            vec!["lookup_order"]
+        );
+    }
+
+    #[tokio::test]
+    async fn org_tools_are_held_to_ssrf_rules() {
+        let tool: HttpToolConfig = serde_json::from_value(serde_json::json!({
+            "name": "internal_hook",
+            "url": "http://127.0.0.1:9/hook",
+        }))
+        .unwrap();
+        let payload = payload(serde_json::json!([
+            { "type": "function", "name": "internal_hook" },
+        ]));
+        let executor = HttpToolExecutor::new(
+            reqwest::Client::new(),
+            &features(),
+            UrlValidationOptions::default(),
+            &payload,
+            vec![tool.clone()],
+            None,
+        );
+        assert!(executor.has_tools());
+
+        let outcome = executor.run_org_tool(&tool, &serde_json::json!({})).await;
+        assert_eq!(outcome.error.as_deref(), Some("blocked url"));
+    }
+
+    #[test]
+    fn detects_declared_calls_from_output_item_done() {
+        let chunk = br#"data: {"type":"response.output_item.done","item":{"type":"function_call","name":"lookup_order","call_id":"call_1","arguments":"{\"order_id\":\"42\"}"}}
+
+"#;
+        let calls = detect_http_tools_in_chunk(chunk, &names(&["lookup_order"]));
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].call_id, "call_1");
+        assert!(calls[0].invalid.is_none());
+        assert_eq!(calls[0].arguments["name"], "lookup_order");
+        assert_eq!(calls[0].arguments["arguments"]["order_id"], "42");
+    }
+
+    #[test]
+    fn ignores_undeclared_calls() {
+        let chunk = br#"data: {"type":"response.output_item.done","item":{"type":"function_call","name":"client_tool","call_id":"call_1","arguments":"{}"}}
+
+"#;
+        assert!(detect_http_tools_in_chunk(chunk, &names(&["lookup_order"])).is_empty());
+    }
+
+    #[test]
+    fn malformed_arguments_are_reported_not_dropped() {
+        let value = serde_json::json!({
+            "type": "function_call",
+            "name": "web_fetch",
+            "call_id": "call_bad",
+            "arguments": "[1, 2]"
+        });
+        let call = parse_http_tool_call(&value, &names(&["web_fetch"])).unwrap();
+        assert!(call.invalid.is_some());
+        assert_eq!(call.arguments["name"], "web_fetch");
+    }
+}
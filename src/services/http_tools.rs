@@ -0,0 +1,84 @@
+use std::{collections::HashMap, sync::Arc};
+
+use uuid::Uuid;
+
+use super::FieldEncryptor;
+use crate::{
+    db::{DbPool, DbResult},
+    models::{CreateHttpTool, HttpTool, UpdateHttpTool},
+};
+
+/// Service layer for HTTP tools registered through the admin API. Header
+/// values often carry credentials, so they're encrypted at rest when field
+/// encryption is configured.
+#[derive(Clone)]
+pub struct HttpToolService {
+    db: Arc<DbPool>,
+    encryption: Option<Arc<FieldEncryptor>>,
+}
+
+impl HttpToolService {
+    pub fn new(db: Arc<DbPool>) -> Self {
+        Self {
+            db,
+            encryption: None,
+        }
+    }
+
+    /// Encrypt header values at rest with the given encryptor.
+    pub fn with_encryption(mut self, encryption: Option<Arc<FieldEncryptor>>) -> Self {
+        self.encryption = encryption;
+        self
+    }
+
+    fn decrypt(&self, mut tool: HttpTool) -> DbResult<HttpTool> {
+        if let Some(enc) = &self.encryption {
+            for value in tool.headers.values_mut() {
+                *value = enc.decrypt(value)?;
+            }
+        }
+        Ok(tool)
+    }
+
+    fn encrypt_headers(&self, headers: &mut HashMap<String, String>) -> DbResult<()> {
+        if let Some(enc) = &self.encryption {
+            for value in headers.values_mut() {
+                *value = enc.encrypt(value)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Register an HTTP tool for an organization
+    pub async fn create(&self, org_id: Uuid, mut input: CreateHttpTool) -> DbResult<HttpTool> {
+        self.encrypt_headers(&mut input.headers)?;
+        let tool = self.db.http_tools().create(org_id, input).await?;
+        self.decrypt(tool)
+    }
+
+    /// Get an HTTP tool by ID
+    pub async fn get_by_id(&self, id: Uuid) -> DbResult<Option<HttpTool>> {
+        let tool = self.db.http_tools().get_by_id(id).await?;
+        tool.map(|t| self.decrypt(t)).transpose()
+    }
+
+    /// An organization's HTTP tools, ordered by name
+    pub async fn list_by_org(&self, org_id: Uuid) -> DbResult<Vec<HttpTool>> {
+        let tools = self.db.http_tools().list_by_org(org_id).await?;
+        tools.into_iter().map(|t| self.decrypt(t)).collect()
+    }
+
+    /// Update an HTTP tool by ID
+    pub async fn update(&self, id: Uuid, mut input: UpdateHttpTool) -> DbResult<HttpTool> {
+        if let Some(headers) = input.headers.as_mut() {
+            self.encrypt_headers(headers)?;
+        }
+        let tool = self.db.http_tools().update(id, input).await?;
+        self.decrypt(tool)
+    }
+
+    /// Delete an HTTP tool by ID
+    pub async fn delete(&self, id: Uuid) -> DbResult<()> {
+        self.db.http_tools().delete(id).await
+    }
+}
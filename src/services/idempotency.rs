@@ -0,0 +1,322 @@
+//! Idempotency keys for the completion endpoints.
+//!
+//! A client that times out waiting for a completion can't tell whether it
+//! ran, so a blind retry risks paying twice. With `[features.idempotency]`
+//! enabled, a request carrying an `Idempotency-Key` header claims the key
+//! for its caller and endpoint; repeats of the key then get the first
+//! request's response replayed, streamed or not, instead of reaching the
+//! provider again. Replays carry no usage headers, so they are not charged.
+//!
+//! Claims live in the `idempotency_keys` table when there is a database, and
+//! in the cache otherwise. Completed responses are also written to the
+//! cache so replays usually skip the database.
+
+use std::{sync::Arc, time::Duration};
+
+use axum::http::HeaderMap;
+use chrono::Utc;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::{
+    cache::{Cache, CacheKeys},
+    config::IdempotencyConfig,
+    db::{DbError, DbPool},
+    models::{IdempotencyRecord, IdempotencyStatus, IdempotentResponse},
+};
+
+/// Request header carrying the client's idempotency key.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+/// Response header marking a replayed response.
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+/// Longest accepted idempotency key.
+pub const MAX_KEY_LENGTH: usize = 255;
+
+/// The client's `Idempotency-Key` header, recorded with its usage.
+pub fn key_from_headers(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .filter(|k| !k.is_empty())
+        .map(String::from)
+}
+
+/// Hash of an idempotency key together with the caller and endpoint it was
+/// sent to, so keys from different callers never collide.
+pub fn key_hash(caller: &str, path: &str, key: &str) -> String {
+    let mut hasher = Sha256::new();
+    for part in [caller, path, key] {
+        hasher.update(part.as_bytes());
+        hasher.update(b"\x00");
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Hash of a request body, to catch a key reused for a different request.
+pub fn request_hash(body: &[u8]) -> String {
+    hex::encode(Sha256::digest(body))
+}
+
+#[derive(Debug, Error)]
+pub enum IdempotencyError {
+    #[error("Database error: {0}")]
+    Db(#[from] DbError),
+
+    #[error("Cache error: {0}")]
+    Cache(String),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+/// Outcome of claiming an idempotency key.
+#[derive(Debug)]
+pub enum Claim {
+    /// The key is new; run the request.
+    Acquired,
+    /// An earlier request with the key is still running.
+    InProgress,
+    /// An earlier request with the key finished. `None` when its response
+    /// was too large to store.
+    Completed(Option<IdempotentResponse>),
+    /// The key was first used with a different request body.
+    Mismatch,
+}
+
+/// Claims and stored responses for idempotency keys.
+pub struct IdempotencyStore {
+    db: Option<Arc<DbPool>>,
+    cache: Option<Arc<dyn Cache>>,
+    ttl: Duration,
+    max_response_bytes: usize,
+}
+
+impl IdempotencyStore {
+    /// `None` when there is neither a database nor a cache to store claims.
+    pub fn new(
+        config: &IdempotencyConfig,
+        db: Option<Arc<DbPool>>,
+        cache: Option<Arc<dyn Cache>>,
+    ) -> Option<Self> {
+        (db.is_some() || cache.is_some()).then(|| Self {
+            db,
+            cache,
+            ttl: Duration::from_secs(config.ttl_secs),
+            max_response_bytes: config.max_response_bytes,
+        })
+    }
+
+    /// Largest response stored for replay.
+    pub fn max_response_bytes(&self) -> usize {
+        self.max_response_bytes
+    }
+
+    /// Claim `key_hash` for a request whose body hashes to `request_hash`.
+    pub async fn claim(
+        &self,
+        key_hash: &str,
+        request_hash: &str,
+    ) -> Result<Claim, IdempotencyError> {
+        let cache_key = CacheKeys::idempotency(key_hash);
+
+        if let Some(cache) = &self.cache
+            && let Some(cached) = self.cached(cache.as_ref(), &cache_key).await?
+            && cached.status == IdempotencyStatus::Completed
+        {
+            return Ok(outcome(cached, request_hash));
+        }
+
+        let now = Utc::now();
+        let record = IdempotencyRecord {
+            key: key_hash.to_string(),
+            request_hash: request_hash.to_string(),
+            status: IdempotencyStatus::InProgress,
+            response: None,
+            created_at: now,
+            expires_at: now + chrono::Duration::seconds(self.ttl.as_secs() as i64),
+        };
+
+        let existing = if let Some(db) = &self.db {
+            db.idempotency_keys().claim(&record).await?
+        } else if let Some(cache) = &self.cache {
+            let claimed = cache
+                .set_nx(&cache_key, &serde_json::to_vec(&record)?, self.ttl)
+                .await
+                .map_err(|e| IdempotencyError::Cache(e.to_string()))?;
+            if claimed {
+                None
+            } else {
+                // The holder released the key between the two calls; report
+                // it as in use and let the client retry
+                match self.cached(cache.as_ref(), &cache_key).await? {
+                    Some(existing) => Some(existing),
+                    None => return Ok(Claim::InProgress),
+                }
+            }
+        } else {
+            None
+        };
+
+        Ok(existing.map_or(Claim::Acquired, |existing| outcome(existing, request_hash)))
+    }
+
+    /// Store the response of the request holding `key_hash`, or mark it
+    /// finished without one when it was too large.
+    pub async fn complete(
+        &self,
+        key_hash: &str,
+        request_hash: &str,
+        response: Option<IdempotentResponse>,
+    ) -> Result<(), IdempotencyError> {
+        if let Some(db) = &self.db {
+            db.idempotency_keys()
+                .complete(key_hash, response.as_ref())
+                .await?;
+        }
+
+        if let Some(cache) = &self.cache {
+            let now = Utc::now();
+            let record = IdempotencyRecord {
+                key: key_hash.to_string(),
+                request_hash: request_hash.to_string(),
+                status: IdempotencyStatus::Completed,
+                response,
+                created_at: now,
+                expires_at: now + chrono::Duration::seconds(self.ttl.as_secs() as i64),
+            };
+            cache
+                .set_bytes(
+                    &CacheKeys::idempotency(key_hash),
+                    &serde_json::to_vec(&record)?,
+                    self.ttl,
+                )
+                .await
+                .map_err(|e| IdempotencyError::Cache(e.to_string()))?;
+        }
+
+        Ok(())
+    }
+
+    /// Drop the claim on `key_hash` so the request can be retried.
+    pub async fn release(&self, key_hash: &str) -> Result<(), IdempotencyError> {
+        if let Some(db) = &self.db {
+            db.idempotency_keys().release(key_hash).await?;
+        } else if let Some(cache) = &self.cache {
+            cache
+                .delete(&CacheKeys::idempotency(key_hash))
+                .await
+                .map_err(|e| IdempotencyError::Cache(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    async fn cached(
+        &self,
+        cache: &dyn Cache,
+        cache_key: &str,
+    ) -> Result<Option<IdempotencyRecord>, IdempotencyError> {
+        let bytes = cache
+            .get_bytes(cache_key)
+            .await
+            .map_err(|e| IdempotencyError::Cache(e.to_string()))?;
+        Ok(bytes
+            .map(|bytes| serde_json::from_slice(&bytes))
+            .transpose()?)
+    }
+}
+
+fn outcome(record: IdempotencyRecord, request_hash: &str) -> Claim {
+    if record.request_hash != request_hash {
+        return Claim::Mismatch;
+    }
+    match record.status {
+        IdempotencyStatus::InProgress => Claim::InProgress,
+        IdempotencyStatus::Completed => Claim::Completed(record.response),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cache::MemoryCache, config::MemoryCacheConfig};
+
+    fn store() -> IdempotencyStore {
+        let cache: Arc<dyn Cache> = Arc::new(MemoryCache::new(&MemoryCacheConfig::default()));
+        IdempotencyStore::new(&IdempotencyConfig::default(), None, Some(cache)).unwrap()
+    }
+
+    fn response() -> IdempotentResponse {
+        IdempotentResponse {
+            status: 200,
+            content_type: Some("text/event-stream".to_string()),
+            body: b"data: [DONE]\n\n".to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_key_hash_is_scoped_to_caller_and_endpoint() {
+        let base = key_hash("key:a", "/v1/chat/completions", "retry-1");
+        assert_eq!(base, key_hash("key:a", "/v1/chat/completions", "retry-1"));
+        assert_ne!(base, key_hash("key:b", "/v1/chat/completions", "retry-1"));
+        assert_ne!(base, key_hash("key:a", "/v1/responses", "retry-1"));
+        assert_ne!(base, key_hash("key:a", "/v1/chat/completions", "retry-2"));
+    }
+
+    #[test]
+    fn test_new_requires_storage() {
+        assert!(IdempotencyStore::new(&IdempotencyConfig::default(), None, None).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_claim_complete_and_replay() {
+        let store = store();
+        assert!(matches!(
+            store.claim("k", "body-1").await.unwrap(),
+            Claim::Acquired
+        ));
+        assert!(matches!(
+            store.claim("k", "body-1").await.unwrap(),
+            Claim::InProgress
+        ));
+
+        store
+            .complete("k", "body-1", Some(response()))
+            .await
+            .unwrap();
+        match store.claim("k", "body-1").await.unwrap() {
+            Claim::Completed(Some(replayed)) => assert_eq!(replayed, response()),
+            other => panic!("expected a stored response, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_claim_with_different_body_is_a_mismatch() {
+        let store = store();
+        store.claim("k", "body-1").await.unwrap();
+        assert!(matches!(
+            store.claim("k", "body-2").await.unwrap(),
+            Claim::Mismatch
+        ));
+
+        store.complete("k", "body-1", None).await.unwrap();
+        assert!(matches!(
+            store.claim("k", "body-2").await.unwrap(),
+            Claim::Mismatch
+        ));
+        assert!(matches!(
+            store.claim("k", "body-1").await.unwrap(),
+            Claim::Completed(None)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_release_allows_retry() {
+        let store = store();
+        store.claim("k", "body-1").await.unwrap();
+        store.release("k").await.unwrap();
+        assert!(matches!(
+            store.claim("k", "body-1").await.unwrap(),
+            Claim::Acquired
+        ));
+    }
+}
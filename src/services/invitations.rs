@@ -0,0 +1,464 @@
+//! Invitations to join an organization, and optionally one of its teams.
+//!
+//! An invitation stores the SHA-256 hash of a random token. The token is
+//! only ever handed out in the invite link, which is returned once by the
+//! create and resend endpoints and optionally delivered by email or
+//! webhook. Invitations are accepted on SSO sign-in (see
+//! [`InvitationService::accept_on_sign_in`]).
+
+use std::sync::Arc;
+
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::{
+    config::InvitationsConfig,
+    db::{DbError, DbPool, DbResult, ListParams, repos::ListResult},
+    models::{
+        AddTeamMember, CreateUser, Invitation, InvitationStatus, MembershipSource, NewInvitation,
+        Organization, Team,
+    },
+    services::notifications::EmailNotifier,
+};
+
+/// Path of the public endpoint invite links point at.
+pub const ACCEPT_PATH: &str = "/auth/invitations/accept";
+
+/// The identity signing in, as asserted by the IdP.
+#[derive(Debug, Clone, Copy)]
+pub struct SignInIdentity<'a> {
+    pub external_id: &'a str,
+    pub email: Option<&'a str>,
+    pub name: Option<&'a str>,
+    /// Organization whose SSO connection authenticated the user.
+    pub sso_org_id: Option<Uuid>,
+}
+
+/// Service layer for organization and team invitations
+#[derive(Clone)]
+pub struct InvitationService {
+    db: Arc<DbPool>,
+}
+
+impl InvitationService {
+    pub fn new(db: Arc<DbPool>) -> Self {
+        Self { db }
+    }
+
+    /// Invite `email` to an organization (and team). Returns the invitation
+    /// and its raw token, which is not stored.
+    ///
+    /// Fails with `Conflict` if the address already has a pending invitation
+    /// to the same organization and team.
+    pub async fn create(
+        &self,
+        org_id: Uuid,
+        team_id: Option<Uuid>,
+        email: &str,
+        role: String,
+        invited_by: Option<Uuid>,
+        expires_at: DateTime<Utc>,
+    ) -> DbResult<(Invitation, String)> {
+        let email = email.trim().to_lowercase();
+        let existing = self
+            .db
+            .invitations()
+            .list_pending_by_email(&email, Utc::now())
+            .await?;
+        if existing
+            .iter()
+            .any(|i| i.org_id == org_id && i.team_id == team_id)
+        {
+            return Err(DbError::Conflict(format!(
+                "'{}' already has a pending invitation; resend it instead",
+                email
+            )));
+        }
+
+        let token = generate_token();
+        let invitation = self
+            .db
+            .invitations()
+            .create(NewInvitation {
+                org_id,
+                team_id,
+                email,
+                role,
+                invited_by,
+                token_hash: hash_token(&token),
+                expires_at,
+            })
+            .await?;
+        Ok((invitation, token))
+    }
+
+    /// Get invitation by ID
+    pub async fn get_by_id(&self, id: Uuid) -> DbResult<Option<Invitation>> {
+        self.db.invitations().get_by_id(id).await
+    }
+
+    /// List an organization's invitations with pagination
+    pub async fn list_by_org(
+        &self,
+        org_id: Uuid,
+        params: ListParams,
+    ) -> DbResult<ListResult<Invitation>> {
+        self.db.invitations().list_by_org(org_id, params).await
+    }
+
+    /// Issue a new token for a pending (or expired) invitation, which
+    /// invalidates the previous link. Returns the invitation and its raw
+    /// token.
+    pub async fn resend(
+        &self,
+        id: Uuid,
+        expires_at: DateTime<Utc>,
+    ) -> DbResult<(Invitation, String)> {
+        let token = generate_token();
+        let invitation = self
+            .db
+            .invitations()
+            .refresh_token(id, &hash_token(&token), expires_at)
+            .await?;
+        Ok((invitation, token))
+    }
+
+    /// Record a delivery of the invitation
+    pub async fn mark_sent(&self, id: Uuid) -> DbResult<()> {
+        self.db.invitations().mark_sent(id).await
+    }
+
+    /// Revoke a pending invitation
+    pub async fn revoke(&self, id: Uuid) -> DbResult<Invitation> {
+        self.db.invitations().revoke(id).await
+    }
+
+    /// Look up an invitation by the raw token from its link
+    pub async fn get_by_token(&self, token: &str) -> DbResult<Option<Invitation>> {
+        self.db
+            .invitations()
+            .get_by_token_hash(&hash_token(token))
+            .await
+    }
+
+    /// Accept invitations for a user who just signed in through SSO, adding
+    /// them to each invitation's organization and team. Returns the
+    /// invitations that were accepted.
+    ///
+    /// Two kinds of invitation are accepted, both only for the invited email
+    /// address:
+    /// - the one whose link the user opened before signing in (`token`),
+    ///   whichever IdP they signed in through, and
+    /// - pending invitations to the organization whose SSO connection
+    ///   authenticated them. Invitations to other organizations need the
+    ///   link, since that IdP doesn't vouch for addresses in them.
+    ///
+    /// The user record is created if this is their first sign-in. Failures
+    /// to accept a single invitation (e.g. because the user already belongs
+    /// to another organization) are logged and skipped.
+    pub async fn accept_on_sign_in(
+        &self,
+        identity: SignInIdentity<'_>,
+        token: Option<&str>,
+    ) -> DbResult<Vec<Invitation>> {
+        let Some(email) = identity.email.map(|e| e.trim().to_lowercase()) else {
+            return Ok(Vec::new());
+        };
+
+        let mut candidates = Vec::new();
+        if let Some(token) = token
+            && let Some(invitation) = self.get_by_token(token).await?
+            && invitation.status == InvitationStatus::Pending
+        {
+            if invitation.email == email {
+                candidates.push(invitation);
+            } else {
+                tracing::warn!(
+                    invitation_id = %invitation.id,
+                    "Invitation link opened by a user signed in with a different email address"
+                );
+            }
+        }
+        if let Some(sso_org_id) = identity.sso_org_id {
+            for invitation in self
+                .db
+                .invitations()
+                .list_pending_by_email(&email, Utc::now())
+                .await?
+            {
+                if invitation.org_id == sso_org_id
+                    && !candidates.iter().any(|c| c.id == invitation.id)
+                {
+                    candidates.push(invitation);
+                }
+            }
+        }
+        if candidates.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let user_id = self.get_or_create_user(identity).await?;
+        let mut accepted = Vec::new();
+        for invitation in candidates {
+            match self.accept(&invitation, user_id).await {
+                Ok(true) => {
+                    if let Some(invitation) = self.get_by_id(invitation.id).await? {
+                        accepted.push(invitation);
+                    }
+                }
+                Ok(false) => {}
+                Err(e) => {
+                    tracing::warn!(
+                        invitation_id = %invitation.id,
+                        user_id = %user_id,
+                        error = %e,
+                        "Failed to accept invitation"
+                    );
+                }
+            }
+        }
+        Ok(accepted)
+    }
+
+    async fn get_or_create_user(&self, identity: SignInIdentity<'_>) -> DbResult<Uuid> {
+        let users = self.db.users();
+        if let Some(user) = users.get_by_external_id(identity.external_id).await? {
+            return Ok(user.id);
+        }
+        let created = users
+            .create(CreateUser {
+                external_id: identity.external_id.to_string(),
+                email: identity.email.map(str::to_string),
+                name: identity.name.map(str::to_string),
+            })
+            .await;
+        match created {
+            Ok(user) => Ok(user.id),
+            // Provisioned concurrently by another request
+            Err(DbError::Conflict(_)) => users
+                .get_by_external_id(identity.external_id)
+                .await?
+                .map(|u| u.id)
+                .ok_or(DbError::NotFound),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Add the user to the invitation's organization and team, skipping
+    /// memberships they already have, then mark it accepted.
+    async fn accept(&self, invitation: &Invitation, user_id: Uuid) -> DbResult<bool> {
+        let users = self.db.users();
+        let is_org_member = users
+            .get_org_memberships_for_user(user_id)
+            .await?
+            .iter()
+            .any(|m| m.org_id == invitation.org_id);
+        if !is_org_member {
+            // Team invitations grant the role on the team only
+            let org_role = if invitation.team_id.is_some() {
+                "member"
+            } else {
+                invitation.role.as_str()
+            };
+            users
+                .add_to_org(
+                    user_id,
+                    invitation.org_id,
+                    org_role,
+                    MembershipSource::Manual,
+                )
+                .await?;
+        }
+
+        if let Some(team_id) = invitation.team_id {
+            let teams = self.db.teams();
+            if !teams.is_member(team_id, user_id).await? {
+                teams
+                    .add_member(
+                        team_id,
+                        AddTeamMember {
+                            user_id,
+                            role: invitation.role.clone(),
+                            source: MembershipSource::Manual,
+                        },
+                    )
+                    .await?;
+            }
+        }
+
+        self.db
+            .invitations()
+            .mark_accepted(invitation.id, user_id)
+            .await
+    }
+}
+
+/// Generate an invitation token: 32 random bytes (256 bits of entropy)
+/// encoded as URL-safe base64.
+fn generate_token() -> String {
+    let mut rng = rand::thread_rng();
+    let mut random_bytes = [0u8; 32];
+    rng.fill(&mut random_bytes);
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(random_bytes)
+}
+
+/// Hash of a token as stored in `invitations.token_hash`.
+fn hash_token(token: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(token.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// The invite link for a token. Relative when no `public_url` is configured.
+pub fn invite_url(config: &InvitationsConfig, token: &str) -> String {
+    let base = config
+        .public_url
+        .as_deref()
+        .map(|u| u.trim_end_matches('/'))
+        .unwrap_or_default();
+    format!("{base}{ACCEPT_PATH}?token={token}")
+}
+
+/// Send an invite link through the configured delivery channel.
+///
+/// Returns `Ok(false)` if no channel is configured.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn deliver(
+    http: &reqwest::Client,
+    config: &InvitationsConfig,
+    notifier: Option<&EmailNotifier>,
+    invitation: &Invitation,
+    org: &Organization,
+    team: Option<&Team>,
+    invite_url: &str,
+) -> Result<bool, String> {
+    use crate::config::InvitationDelivery;
+
+    let Some(delivery) = &config.delivery else {
+        return Ok(false);
+    };
+
+    match delivery {
+        InvitationDelivery::Webhook {
+            url,
+            bearer_token,
+            signing_secret,
+            timeout_secs,
+        } => {
+            use super::responses_webhook::{SIGNATURE_HEADER, sign_payload};
+
+            let body = serde_json::to_vec(&serde_json::json!({
+                "type": "invitation",
+                "invitation": invitation,
+                "organization": {"id": org.id, "slug": org.slug, "name": org.name},
+                "team": team.map(|t| serde_json::json!({"id": t.id, "slug": t.slug, "name": t.name})),
+                "invite_url": invite_url,
+            }))
+            .map_err(|e| e.to_string())?;
+
+            let mut req = http
+                .post(url)
+                .timeout(std::time::Duration::from_secs(*timeout_secs))
+                .header("Content-Type", "application/json")
+                .header("User-Agent", "hadrian-invitations/1");
+            if let Some(secret) = signing_secret {
+                req = req.header(SIGNATURE_HEADER, sign_payload(secret, &body, Utc::now()));
+            }
+            if let Some(token) = bearer_token {
+                req = req.bearer_auth(token);
+            }
+
+            let resp = req.body(body).send().await.map_err(|e| e.to_string())?;
+            if !resp.status().is_success() {
+                return Err(format!("webhook returned HTTP {}", resp.status()));
+            }
+            Ok(true)
+        }
+        InvitationDelivery::Email => {
+            use crate::{models::EmailKind, services::notifications::Notification};
+
+            let notifier = notifier.ok_or("email delivery requires [notifications.email]")?;
+            let target = match team {
+                Some(team) => format!("the {} team in {}", team.name, org.name),
+                None => org.name.clone(),
+            };
+            notifier
+                .send(Notification {
+                    kind: EmailKind::Invitation,
+                    org: Some(org),
+                    to: vec![invitation.email.clone()],
+                    vars: vec![
+                        ("org_name", org.name.clone()),
+                        (
+                            "team_name",
+                            team.map(|t| t.name.clone()).unwrap_or_default(),
+                        ),
+                        ("target", target),
+                        ("role", invitation.role.clone()),
+                        ("invite_url", invite_url.to_string()),
+                        (
+                            "expires_at",
+                            invitation
+                                .expires_at
+                                .format("%Y-%m-%d %H:%M UTC")
+                                .to_string(),
+                        ),
+                    ],
+                })
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok(true)
+        }
+    }
+}
+
+/// Invitation delivery isn't available in WASM builds.
+#[cfg(target_arch = "wasm32")]
+pub async fn deliver(
+    _http: &reqwest::Client,
+    config: &InvitationsConfig,
+    _notifier: Option<&EmailNotifier>,
+    _invitation: &Invitation,
+    _org: &Organization,
+    _team: Option<&Team>,
+    _invite_url: &str,
+) -> Result<bool, String> {
+    match config.delivery {
+        Some(_) => Err("Invitation delivery is not supported in this build".to_string()),
+        None => Ok(false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invite_url() {
+        let mut config = InvitationsConfig::default();
+        assert_eq!(
+            invite_url(&config, "abc"),
+            "/auth/invitations/accept?token=abc"
+        );
+
+        config.public_url = Some("https://gateway.example.com/".to_string());
+        assert_eq!(
+            invite_url(&config, "abc"),
+            "https://gateway.example.com/auth/invitations/accept?token=abc"
+        );
+    }
+
+    #[test]
+    fn test_tokens_are_unique_and_hashed() {
+        let a = generate_token();
+        let b = generate_token();
+        assert_ne!(a, b);
+        assert_eq!(a.len(), 43);
+        assert_eq!(hash_token(&a), hash_token(&a));
+        assert_eq!(hash_token(&a).len(), 64);
+        assert_ne!(hash_token(&a), a);
+    }
+}
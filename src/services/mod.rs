@@ -1,14 +1,21 @@
 mod access_reviews;
+#[cfg(feature = "server")]
+pub mod agents;
 mod api_keys;
 pub mod audit_logs;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod background_executor;
+mod bundles;
+mod client_cert_mappings;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod compactor;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod container_session;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod containers;
+pub mod context_compression;
+#[cfg(not(target_arch = "wasm32"))]
+mod conversation_summarizer;
 mod conversations;
 #[cfg(any(
     feature = "document-extraction-basic",
@@ -17,31 +24,56 @@ mod conversations;
 pub mod document_processor;
 #[cfg(feature = "sso")]
 mod domain_verifications;
+mod elevations;
+#[cfg(all(feature = "smtp", not(target_arch = "wasm32")))]
+pub mod email;
+pub mod embedding_batcher;
+mod entitlements;
+mod federation;
+mod field_encryption;
 mod file_search;
 pub mod file_search_tool;
 mod file_storage;
 mod files;
+#[cfg(feature = "server")]
+pub mod fine_tuning;
 #[cfg(feature = "forecasting")]
 pub mod forecasting;
+#[cfg(feature = "server")]
+pub mod http_tool;
+mod http_tools;
+pub mod idempotency;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod input_file_staging;
+pub mod invitations;
 #[cfg(all(feature = "mcp", not(target_arch = "wasm32")))]
 pub mod mcp;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod mcp_tool;
+pub mod model_degradation;
 mod model_pricing;
+pub mod notifications;
 pub mod oauth_pkce;
 mod org_rbac_policies;
+mod org_request_policies;
 #[cfg(feature = "sso")]
 mod org_sso_configs;
 mod organizations;
+mod passkeys;
 mod projects;
 #[cfg(feature = "prometheus")]
 pub mod prometheus_client;
 #[cfg(feature = "prometheus")]
 pub mod prometheus_parser;
+#[cfg(feature = "server")]
+pub mod provenance;
+#[cfg(feature = "server")]
+pub mod provider_files;
 pub mod provider_metrics;
 mod providers;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod replay;
+mod report_runs;
 mod reranker;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod response_event_buffer;
@@ -55,6 +87,10 @@ pub mod responses_pipeline;
 mod responses_store;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod responses_webhook;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod scheduled_reports;
+#[cfg(feature = "server")]
+pub mod scheduled_tasks;
 #[cfg(feature = "sso")]
 mod scim_configs;
 #[cfg(feature = "sso")]
@@ -63,17 +99,31 @@ pub mod server_tool_history;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod server_tools;
 mod service_accounts;
+mod shadow_results;
+#[cfg(feature = "server")]
+pub mod shadow_traffic;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod shell_tool;
 #[cfg(feature = "server")]
 pub mod skill_zip;
 mod skills;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod source_sync;
 #[cfg(feature = "sso")]
 mod sso_group_mappings;
+mod step_up;
+pub mod structured_output;
 mod teams;
 mod templates;
+pub mod token_counter;
 mod usage;
+pub mod usage_anomalies;
+mod usage_imports;
+pub mod usage_reconciliation;
+pub mod usage_reliability;
+pub mod usage_tags;
 mod users;
+mod vector_store_syncs;
 mod vector_stores;
 #[cfg(feature = "virus-scan")]
 mod virus_scan;
@@ -84,6 +134,10 @@ use std::sync::Arc;
 pub use access_reviews::AccessReviewService;
 pub use api_keys::ApiKeyService;
 pub use audit_logs::AuditLogService;
+pub use bundles::{BundleError, BundleService};
+pub use client_cert_mappings::ClientCertMappingService;
+#[cfg(not(target_arch = "wasm32"))]
+pub use conversation_summarizer::{ConversationSummarizer, GeneratedSummary, SummarizeError};
 pub use conversations::ConversationService;
 #[cfg(any(
     feature = "document-extraction-basic",
@@ -95,6 +149,12 @@ pub use document_processor::{
 };
 #[cfg(feature = "sso")]
 pub use domain_verifications::{DomainVerificationError, DomainVerificationService};
+pub use elevations::ElevationService;
+#[cfg(all(feature = "smtp", not(target_arch = "wasm32")))]
+pub use email::{EmailAttachment, EmailError, EmailSender, OutgoingEmail};
+pub use entitlements::{EntitlementError, EntitlementService};
+pub use federation::FederationService;
+pub use field_encryption::{FieldEncryptionError, FieldEncryptor};
 pub use file_search::{
     FileSearchError, FileSearchRequest, FileSearchResponse, FileSearchResult, FileSearchService,
     FileSearchServiceConfig,
@@ -110,12 +170,17 @@ pub use file_storage::{
     DatabaseFileStorage, FileStorage, FileStorageError, FileStorageResult, create_file_storage,
 };
 pub use files::{FilesService, FilesServiceError, FilesServiceResult};
+pub use http_tools::HttpToolService;
+pub use invitations::InvitationService;
 pub use model_pricing::ModelPricingService;
+pub use notifications::{EmailNotifier, Notification, NotifyError};
 pub use oauth_pkce::{OAuthPkceError, OAuthPkceService};
 pub use org_rbac_policies::{OrgRbacPolicyError, OrgRbacPolicyService};
+pub use org_request_policies::{OrgRequestPolicyError, OrgRequestPolicyService};
 #[cfg(feature = "sso")]
 pub use org_sso_configs::{OrgSsoConfigError, OrgSsoConfigService, OrgSsoConfigWithClientSecret};
 pub use organizations::OrganizationService;
+pub use passkeys::PasskeyService;
 pub use projects::ProjectService;
 pub use provider_metrics::{
     ProviderMetricsError, ProviderMetricsService, ProviderStats, ProviderStatsHistorical,
@@ -125,6 +190,7 @@ pub use providers::{
     DynamicProviderError, DynamicProviderService, validate_provider_config_with_url,
     validate_provider_type,
 };
+pub use report_runs::ReportRunService;
 pub use reranker::{
     LlmReranker, NoOpReranker, RankedResult, RerankError, RerankRequest, RerankResponse,
     RerankUsage, Reranker,
@@ -137,18 +203,27 @@ pub use responses_store::{
 };
 #[cfg(not(target_arch = "wasm32"))]
 pub use responses_webhook::{ResponsesWebhookDispatcher, WebhookEvent, WebhookEventData};
+#[cfg(not(target_arch = "wasm32"))]
+pub use scheduled_reports::{ReportError, ReportPeriod, ScheduledReportService};
 #[cfg(feature = "sso")]
 pub use scim_configs::{OrgScimConfigError, OrgScimConfigService};
 #[cfg(feature = "sso")]
 pub use scim_provisioning::ScimProvisioningService;
 pub use service_accounts::ServiceAccountService;
+pub use shadow_results::ShadowResultService;
 pub use skills::SkillService;
+#[cfg(not(target_arch = "wasm32"))]
+pub use source_sync::{SourceSyncError, SourceSyncService};
 #[cfg(feature = "sso")]
 pub use sso_group_mappings::SsoGroupMappingService;
+pub use step_up::{StepUpError, StepUpService};
 pub use teams::TeamService;
 pub use templates::TemplateService;
 pub use usage::UsageService;
+pub use usage_anomalies::UsageAnomalyService;
+pub use usage_imports::{ProviderUsageImportService, UsageImportError};
 pub use users::UserService;
+pub use vector_store_syncs::VectorStoreSyncService;
 pub use vector_stores::VectorStoresService;
 #[cfg(feature = "virus-scan")]
 pub use virus_scan::{
@@ -171,10 +246,17 @@ pub struct Services {
     pub model_pricing: ModelPricingService,
     pub conversations: ConversationService,
     pub templates: TemplateService,
+    pub http_tools: HttpToolService,
     pub skills: SkillService,
     pub audit_logs: AuditLogService,
+    pub bundles: BundleService,
     pub access_reviews: AccessReviewService,
+    pub report_runs: ReportRunService,
+    pub federation: FederationService,
+    pub step_up: StepUpService,
+    pub passkeys: PasskeyService,
     pub vector_stores: VectorStoresService,
+    pub vector_store_syncs: VectorStoreSyncService,
     pub files: FilesService,
     #[cfg(feature = "sso")]
     pub sso_group_mappings: SsoGroupMappingService,
@@ -187,7 +269,15 @@ pub struct Services {
     #[cfg(feature = "sso")]
     pub scim_provisioning: ScimProvisioningService,
     pub org_rbac_policies: OrgRbacPolicyService,
+    pub org_request_policies: OrgRequestPolicyService,
+    pub shadow_results: ShadowResultService,
+    pub usage_anomalies: UsageAnomalyService,
+    pub provider_usage_imports: ProviderUsageImportService,
     pub service_accounts: ServiceAccountService,
+    pub client_cert_mappings: ClientCertMappingService,
+    pub invitations: InvitationService,
+    pub elevations: ElevationService,
+    pub entitlements: EntitlementService,
     pub oauth_pkce: OAuthPkceService,
 }
 
@@ -209,10 +299,17 @@ impl Services {
             model_pricing: ModelPricingService::new(db.clone()),
             conversations: ConversationService::new(db.clone()),
             templates: TemplateService::new(db.clone()),
+            http_tools: HttpToolService::new(db.clone()),
             skills: SkillService::new(db.clone(), max_skill_bytes),
             audit_logs: AuditLogService::new(db.clone()),
+            bundles: BundleService::new(db.clone(), max_expression_length),
             access_reviews: AccessReviewService::new(db.clone()),
+            report_runs: ReportRunService::new(db.clone()),
+            federation: FederationService::new(db.clone()),
+            step_up: StepUpService::new(db.clone()),
+            passkeys: PasskeyService::new(db.clone()),
             vector_stores: VectorStoresService::new(db.clone()),
+            vector_store_syncs: VectorStoreSyncService::new(db.clone()),
             #[cfg(feature = "sso")]
             sso_group_mappings: SsoGroupMappingService::new(db.clone()),
             #[cfg(feature = "sso")]
@@ -224,7 +321,15 @@ impl Services {
             #[cfg(feature = "sso")]
             scim_provisioning: ScimProvisioningService::new(db.clone()),
             org_rbac_policies: OrgRbacPolicyService::new(db.clone(), max_expression_length),
+            org_request_policies: OrgRequestPolicyService::new(db.clone(), max_expression_length),
+            shadow_results: ShadowResultService::new(db.clone()),
+            usage_anomalies: UsageAnomalyService::new(db.clone()),
+            provider_usage_imports: ProviderUsageImportService::new(db.clone()),
             service_accounts: ServiceAccountService::new(db.clone()),
+            client_cert_mappings: ClientCertMappingService::new(db.clone()),
+            invitations: InvitationService::new(db.clone()),
+            elevations: ElevationService::new(db.clone()),
+            entitlements: EntitlementService::new(db.clone()),
             oauth_pkce: OAuthPkceService::new(db.clone()),
             files: FilesService::new(db, file_storage),
         }
@@ -249,10 +354,17 @@ impl Services {
             model_pricing: ModelPricingService::new(db.clone()),
             conversations: ConversationService::new(db.clone()),
             templates: TemplateService::new(db.clone()),
+            http_tools: HttpToolService::new(db.clone()),
             skills: SkillService::new(db.clone(), max_skill_bytes),
             audit_logs: AuditLogService::with_event_bus(db.clone(), event_bus),
+            bundles: BundleService::new(db.clone(), max_expression_length),
             access_reviews: AccessReviewService::new(db.clone()),
+            report_runs: ReportRunService::new(db.clone()),
+            federation: FederationService::new(db.clone()),
+            step_up: StepUpService::new(db.clone()),
+            passkeys: PasskeyService::new(db.clone()),
             vector_stores: VectorStoresService::new(db.clone()),
+            vector_store_syncs: VectorStoreSyncService::new(db.clone()),
             #[cfg(feature = "sso")]
             sso_group_mappings: SsoGroupMappingService::new(db.clone()),
             #[cfg(feature = "sso")]
@@ -264,7 +376,15 @@ impl Services {
             #[cfg(feature = "sso")]
             scim_provisioning: ScimProvisioningService::new(db.clone()),
             org_rbac_policies: OrgRbacPolicyService::new(db.clone(), max_expression_length),
+            org_request_policies: OrgRequestPolicyService::new(db.clone(), max_expression_length),
+            shadow_results: ShadowResultService::new(db.clone()),
+            usage_anomalies: UsageAnomalyService::new(db.clone()),
+            provider_usage_imports: ProviderUsageImportService::new(db.clone()),
             service_accounts: ServiceAccountService::new(db.clone()),
+            client_cert_mappings: ClientCertMappingService::new(db.clone()),
+            invitations: InvitationService::new(db.clone()),
+            elevations: ElevationService::new(db.clone()),
+            entitlements: EntitlementService::new(db.clone()),
             oauth_pkce: OAuthPkceService::new(db.clone()),
             files: FilesService::new(db, file_storage),
         }
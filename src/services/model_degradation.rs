@@ -0,0 +1,94 @@
+//! Model degradation ladders for chat completions.
+//!
+//! An organization with a [`ModelDegradationPolicy`] has requests for a
+//! premium model moved down that model's ladder instead of failing:
+//!
+//! - **`circuit_open`**: the requested model's provider has an open circuit
+//!   breaker, so the request goes straight to the first available rung.
+//! - **`over_budget`**: the API key's spend passed the budget warning
+//!   threshold. Exhausted budgets still reject the request.
+//! - **`rate_limited`**: the provider answered `429`, after any configured
+//!   fallbacks, so the request is retried on the next rung.
+//!
+//! Degraded responses carry [`DEGRADED_FROM_HEADER`] and
+//! [`DEGRADATION_REASON_HEADER`], which are also recorded with the request's
+//! usage.
+
+use axum::http::{HeaderMap, HeaderValue};
+
+use crate::{
+    AppState,
+    models::{DegradationTrigger, ModelDegradationPolicy},
+    providers::circuit_breaker::CircuitState,
+    routing::{RoutedProvider, route_model_extended},
+};
+
+/// Response header with the model the client asked for.
+pub const DEGRADED_FROM_HEADER: &str = "X-Model-Degraded-From";
+
+/// Response header with the trigger that degraded the request.
+pub const DEGRADATION_REASON_HEADER: &str = "X-Model-Degradation-Reason";
+
+/// Request extension set by the API middleware when the caller's spend has
+/// passed the budget warning threshold.
+#[derive(Debug, Clone, Copy)]
+pub struct BudgetPressure;
+
+/// A downgrade applied to a request.
+#[derive(Debug, Clone)]
+pub struct Degradation {
+    /// Model the client asked for
+    pub from_model: String,
+    /// Position in the ladder's `downgrades` of the model the request was
+    /// sent to
+    pub rung: usize,
+    pub reason: DegradationTrigger,
+}
+
+impl Degradation {
+    /// Annotate a response with the model the client asked for and why it
+    /// wasn't used.
+    pub fn insert_headers(&self, headers: &mut HeaderMap) {
+        if let Ok(value) = HeaderValue::from_str(&self.from_model) {
+            headers.insert(DEGRADED_FROM_HEADER, value);
+        }
+        headers.insert(
+            DEGRADATION_REASON_HEADER,
+            HeaderValue::from_static(self.reason.as_str()),
+        );
+    }
+}
+
+/// The trigger that should degrade a request before it's dispatched, if any.
+///
+/// `rate_limited` is only known once the provider has answered, so it isn't
+/// considered here.
+pub fn pre_dispatch_trigger(
+    state: &AppState,
+    policy: &ModelDegradationPolicy,
+    model: &str,
+    budget_pressure: bool,
+) -> Option<DegradationTrigger> {
+    if policy.triggers_on(DegradationTrigger::CircuitOpen) && circuit_open(state, model) {
+        return Some(DegradationTrigger::CircuitOpen);
+    }
+    if policy.triggers_on(DegradationTrigger::OverBudget) && budget_pressure {
+        return Some(DegradationTrigger::OverBudget);
+    }
+    None
+}
+
+/// Whether the provider a model routes to has an open circuit breaker.
+///
+/// Unroutable models and providers without a breaker count as closed.
+pub fn circuit_open(state: &AppState, model: &str) -> bool {
+    let provider = match route_model_extended(Some(model), &state.config.providers) {
+        Ok(RoutedProvider::Static(route)) => route.provider_name.to_string(),
+        Ok(RoutedProvider::Dynamic(route)) => route.provider_name,
+        Err(_) => return false,
+    };
+    state
+        .circuit_breakers
+        .get(&provider)
+        .is_some_and(|breaker| breaker.state() == CircuitState::Open)
+}
@@ -0,0 +1,442 @@
+//! Notification emails.
+//!
+//! [`EmailNotifier`] renders a notification from its template, picks the
+//! organization's sender address, applies the send rate limit, sends it over
+//! SMTP and records the attempt in the email send log. Invitations, spend
+//! alerts, access review reminders and DLQ failure notifications all go
+//! through it.
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    config::{EmailConfig, EmailTemplate, SpendAlertNotifications},
+    db::{DbError, DbPool},
+    models::{EmailKind, EmailSendStatus, NewEmailLogEntry, Organization},
+    observability::metrics,
+};
+
+#[derive(Debug, Error)]
+pub enum NotifyError {
+    #[error("No recipients")]
+    NoRecipients,
+
+    #[error("Email send rate limit exceeded")]
+    RateLimited,
+
+    #[error("{0}")]
+    Send(String),
+
+    #[error(transparent)]
+    Database(#[from] DbError),
+}
+
+/// A notification to render and send.
+#[derive(Debug, Clone)]
+pub struct Notification<'a> {
+    pub kind: EmailKind,
+    /// Organization the email is about. Selects the sender address and is
+    /// recorded in the send log.
+    pub org: Option<&'a Organization>,
+    pub to: Vec<String>,
+    /// Template variables, substituted for `{{name}}` placeholders.
+    pub vars: Vec<(&'static str, String)>,
+}
+
+/// Sends notification emails through `[notifications.email]`.
+pub struct EmailNotifier {
+    config: EmailConfig,
+    db: Option<Arc<DbPool>>,
+    limiter: SendRateLimiter,
+    #[cfg(all(feature = "smtp", not(target_arch = "wasm32")))]
+    sender: super::email::EmailSender,
+}
+
+impl EmailNotifier {
+    pub fn new(config: &EmailConfig, db: Option<Arc<DbPool>>) -> Result<Self, String> {
+        Ok(Self {
+            #[cfg(all(feature = "smtp", not(target_arch = "wasm32")))]
+            sender: super::email::EmailSender::new(config).map_err(|e| e.to_string())?,
+            limiter: SendRateLimiter::new(config.rate_limit_per_minute),
+            config: config.clone(),
+            db,
+        })
+    }
+
+    /// Render and send a notification, recording the attempt in the send
+    /// log. Failures are returned for the caller to report; they're already
+    /// logged.
+    pub async fn send(&self, notification: Notification<'_>) -> Result<(), NotifyError> {
+        let Notification {
+            kind,
+            org,
+            to,
+            vars,
+        } = notification;
+        if to.is_empty() {
+            return Err(NotifyError::NoRecipients);
+        }
+
+        let (subject, text) = self.render(kind, &vars);
+        let from = self
+            .config
+            .from_for_org(org.map(|o| o.slug.as_str()))
+            .to_string();
+
+        let result = if self.limiter.try_acquire() {
+            self.deliver(&from, to.clone(), subject.clone(), text).await
+        } else {
+            Err(NotifyError::RateLimited)
+        };
+
+        let (status, error) = match &result {
+            Ok(()) => (EmailSendStatus::Sent, None),
+            Err(NotifyError::RateLimited) => (EmailSendStatus::RateLimited, None),
+            Err(e) => (EmailSendStatus::Failed, Some(e.to_string())),
+        };
+        metrics::record_email_send(kind.as_str(), status.as_str());
+        if let Some(ref error) = error {
+            tracing::warn!(kind = kind.as_str(), error = %error, "Failed to send notification email");
+        } else if status == EmailSendStatus::RateLimited {
+            tracing::warn!(
+                kind = kind.as_str(),
+                limit = self.config.rate_limit_per_minute,
+                "Notification email dropped by the send rate limit"
+            );
+        }
+
+        if let Some(db) = &self.db
+            && let Err(e) = db
+                .email_log()
+                .create(NewEmailLogEntry {
+                    org_id: org.map(|o| o.id),
+                    kind,
+                    from_address: from,
+                    recipients: to,
+                    subject,
+                    status,
+                    error,
+                })
+                .await
+        {
+            tracing::warn!(error = %e, "Failed to record email send attempt");
+        }
+
+        result
+    }
+
+    /// Subject and body of a notification: the configured template, falling
+    /// back to the built-in text per field.
+    fn render(&self, kind: EmailKind, vars: &[(&'static str, String)]) -> (String, String) {
+        let templates = &self.config.templates;
+        let configured: Option<&EmailTemplate> = match kind {
+            EmailKind::Invitation => templates.invitation.as_ref(),
+            EmailKind::SpendAlert => templates.spend_alert.as_ref(),
+            EmailKind::AccessReviewReminder => templates.access_review_reminder.as_ref(),
+            EmailKind::DlqFailure => templates.dlq_failure.as_ref(),
+        };
+        let (default_subject, default_text) = default_template(kind);
+        let subject = configured
+            .and_then(|t| t.subject.as_deref())
+            .unwrap_or(default_subject);
+        let text = configured
+            .and_then(|t| t.text.as_deref())
+            .unwrap_or(default_text);
+
+        // Line breaks in a variable mustn't leak into the subject header
+        let subject = render(subject, vars).replace(['\r', '\n'], " ");
+        (subject, render(text, vars))
+    }
+
+    #[cfg(all(feature = "smtp", not(target_arch = "wasm32")))]
+    async fn deliver(
+        &self,
+        from: &str,
+        to: Vec<String>,
+        subject: String,
+        text: String,
+    ) -> Result<(), NotifyError> {
+        self.sender
+            .send_from(
+                from,
+                super::email::OutgoingEmail {
+                    to,
+                    subject,
+                    text,
+                    attachments: Vec::new(),
+                },
+            )
+            .await
+            .map_err(|e| NotifyError::Send(e.to_string()))
+    }
+
+    #[cfg(not(all(feature = "smtp", not(target_arch = "wasm32"))))]
+    async fn deliver(
+        &self,
+        _from: &str,
+        _to: Vec<String>,
+        _subject: String,
+        _text: String,
+    ) -> Result<(), NotifyError> {
+        Err(NotifyError::Send(
+            "email notifications require the 'smtp' feature".to_string(),
+        ))
+    }
+}
+
+/// A budget warning to email, see [`send_spend_alert`].
+#[derive(Debug, Clone)]
+pub struct SpendAlert<'a> {
+    pub org_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+    /// What the budget belongs to, e.g. `API key "ci" (hk_ab12)`.
+    pub scope: String,
+    pub spend_percentage: f64,
+    pub current_spend_cents: i64,
+    pub limit_cents: i64,
+    pub period: &'a str,
+}
+
+/// Email a spend alert to the members of the budget's organization holding
+/// `[notifications.spend_alerts] roles`, plus its `to` addresses. Budgets
+/// outside any organization only alert the `to` addresses.
+pub async fn send_spend_alert(
+    notifier: &EmailNotifier,
+    db: &DbPool,
+    config: &SpendAlertNotifications,
+    alert: SpendAlert<'_>,
+) -> Result<(), NotifyError> {
+    let org_id = match (alert.org_id, alert.project_id) {
+        (Some(org_id), _) => Some(org_id),
+        (None, Some(project_id)) => db.projects().get_by_id(project_id).await?.map(|p| p.org_id),
+        (None, None) => None,
+    };
+    let org = match org_id {
+        Some(org_id) => db.organizations().get_by_id(org_id).await?,
+        None => None,
+    };
+
+    let mut to = match &org {
+        Some(org) => {
+            db.users()
+                .list_org_member_emails(org.id, &config.roles)
+                .await?
+        }
+        None => Vec::new(),
+    };
+    to.extend(config.to.iter().cloned());
+    to.sort();
+    to.dedup();
+
+    notifier
+        .send(Notification {
+            kind: EmailKind::SpendAlert,
+            org: org.as_ref(),
+            to,
+            vars: vec![
+                (
+                    "org_name",
+                    org.as_ref()
+                        .map(|o| o.name.clone())
+                        .unwrap_or_else(|| "your organization".to_string()),
+                ),
+                ("scope", alert.scope),
+                (
+                    "spend_percent",
+                    format!("{:.0}", alert.spend_percentage * 100.0),
+                ),
+                ("current_spend", format_cents(alert.current_spend_cents)),
+                ("limit", format_cents(alert.limit_cents)),
+                ("period", alert.period.to_string()),
+            ],
+        })
+        .await
+}
+
+fn format_cents(cents: i64) -> String {
+    format!("${}.{:02}", cents / 100, (cents % 100).abs())
+}
+
+/// Built-in subject and body for each kind of notification.
+fn default_template(kind: EmailKind) -> (&'static str, &'static str) {
+    match kind {
+        EmailKind::Invitation => (
+            "You're invited to join {{org_name}}",
+            "You've been invited to join {{target}} as {{role}}.\n\n\
+             Open this link and sign in to accept:\n{{invite_url}}\n\n\
+             The link expires on {{expires_at}}.",
+        ),
+        EmailKind::SpendAlert => (
+            "{{org_name}}: {{spend_percent}}% of the {{period}} budget used",
+            "{{scope}} in {{org_name}} has spent {{current_spend}} of its {{period}} budget \
+             of {{limit}} ({{spend_percent}}%).\n\n\
+             Requests are rejected once the budget is used up. Raise the budget or reduce \
+             usage to avoid interruptions.",
+        ),
+        EmailKind::AccessReviewReminder => (
+            "{{org_name}}: access review reminder",
+            "Some access in {{org_name}} hasn't been used in {{inactive_days}} days:\n\n\
+             - {{stale_users}} inactive users\n\
+             - {{never_active_users}} users who never signed in\n\
+             - {{stale_api_keys}} unused API keys\n\n\
+             {{details}}\n\
+             Review it and remove any access that's no longer needed.",
+        ),
+        EmailKind::DlqFailure => (
+            "Dead-letter queue entry {{entry_id}} failed permanently",
+            "The {{entry_type}} entry {{entry_id}}, queued at {{created_at}}, failed after \
+             {{retry_count}} retries and won't be retried again.\n\n\
+             Last error: {{error}}\n\n\
+             Inspect or redrive it through /admin/v1/dlq.",
+        ),
+    }
+}
+
+/// Substitute `{{name}}` placeholders (surrounding whitespace allowed).
+/// Unknown placeholders are left as-is.
+fn render(template: &str, vars: &[(&'static str, String)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            rest = &rest[start..];
+            break;
+        };
+        let name = after[..end].trim();
+        match vars.iter().find(|(k, _)| *k == name) {
+            Some((_, value)) => out.push_str(value),
+            None => out.push_str(&rest[start..start + 2 + end + 2]),
+        }
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Fixed one-minute sliding window over this instance's sends.
+struct SendRateLimiter {
+    limit: u32,
+    sent: Mutex<VecDeque<Instant>>,
+}
+
+impl SendRateLimiter {
+    const WINDOW: Duration = Duration::from_secs(60);
+
+    fn new(limit: u32) -> Self {
+        Self {
+            limit,
+            sent: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    fn try_acquire(&self) -> bool {
+        self.try_acquire_at(Instant::now())
+    }
+
+    fn try_acquire_at(&self, now: Instant) -> bool {
+        if self.limit == 0 {
+            return true;
+        }
+        let mut sent = self.sent.lock().unwrap_or_else(|e| e.into_inner());
+        while sent
+            .front()
+            .is_some_and(|t| now.duration_since(*t) >= Self::WINDOW)
+        {
+            sent.pop_front();
+        }
+        if sent.len() >= self.limit as usize {
+            return false;
+        }
+        sent.push_back(now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars() -> Vec<(&'static str, String)> {
+        vec![
+            ("org_name", "Acme".to_string()),
+            ("role", "admin".to_string()),
+        ]
+    }
+
+    #[test]
+    fn test_render_substitutes_known_placeholders() {
+        assert_eq!(
+            render("Join {{org_name}} as {{ role }}", &vars()),
+            "Join Acme as admin"
+        );
+        assert_eq!(
+            render("{{unknown}} and {{org_name}}", &vars()),
+            "{{unknown}} and Acme"
+        );
+        assert_eq!(
+            render("Unclosed {{org_name", &vars()),
+            "Unclosed {{org_name"
+        );
+        assert_eq!(render("No placeholders", &vars()), "No placeholders");
+    }
+
+    #[test]
+    fn test_templates_fall_back_per_field() {
+        let config: EmailConfig = toml::from_str(
+            r#"
+            host = "smtp.example.com"
+            from = "noreply@example.com"
+            tls = "none"
+
+            [templates.invitation]
+            subject = "Welcome to {{org_name}}"
+            "#,
+        )
+        .unwrap();
+        let notifier = EmailNotifier::new(&config, None).unwrap();
+
+        let (subject, text) = notifier.render(
+            EmailKind::Invitation,
+            &[
+                ("org_name", "Acme\nCorp".to_string()),
+                ("target", "Acme".to_string()),
+                ("role", "member".to_string()),
+                ("invite_url", "https://gw.example.com/x".to_string()),
+                ("expires_at", "2026-01-01 00:00 UTC".to_string()),
+            ],
+        );
+        assert_eq!(subject, "Welcome to Acme Corp");
+        assert!(text.contains("join Acme as member"));
+        assert!(text.contains("https://gw.example.com/x"));
+    }
+
+    #[test]
+    fn test_format_cents() {
+        assert_eq!(format_cents(0), "$0.00");
+        assert_eq!(format_cents(1205), "$12.05");
+        assert_eq!(format_cents(100_000), "$1000.00");
+    }
+
+    #[test]
+    fn test_rate_limiter_window() {
+        let limiter = SendRateLimiter::new(2);
+        let start = Instant::now();
+        assert!(limiter.try_acquire_at(start));
+        assert!(limiter.try_acquire_at(start + Duration::from_secs(1)));
+        assert!(!limiter.try_acquire_at(start + Duration::from_secs(2)));
+        // The first send leaves the window
+        assert!(limiter.try_acquire_at(start + Duration::from_secs(60)));
+        assert!(!limiter.try_acquire_at(start + Duration::from_secs(60)));
+
+        let unlimited = SendRateLimiter::new(0);
+        assert!((0..100).all(|_| unlimited.try_acquire_at(start)));
+    }
+}
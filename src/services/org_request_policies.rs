@@ -0,0 +1,167 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    authz::{AuthzEngine, RequestPolicyRegistry},
+    db::{DbPool, DbResult},
+    models::{
+        CreateOrgRequestPolicy, OrgRequestPolicy, RequestPolicyAction, UpdateOrgRequestPolicy,
+    },
+};
+
+/// Service layer for organization request policies.
+///
+/// Validates CEL conditions and the action/modifications pairing before
+/// saving, and invalidates the enforcement cache after every change.
+#[derive(Clone)]
+pub struct OrgRequestPolicyService {
+    db: Arc<DbPool>,
+    max_expression_length: usize,
+}
+
+impl OrgRequestPolicyService {
+    pub fn new(db: Arc<DbPool>, max_expression_length: usize) -> Self {
+        Self {
+            db,
+            max_expression_length,
+        }
+    }
+
+    /// Create a request policy for an organization.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The CEL condition is invalid
+    /// - `modifications` is missing for `modify`, or present for another action
+    /// - A policy with the same name already exists in the org
+    pub async fn create(
+        &self,
+        org_id: Uuid,
+        input: CreateOrgRequestPolicy,
+    ) -> Result<OrgRequestPolicy, OrgRequestPolicyError> {
+        self.validate(&input)?;
+
+        Ok(self.db.org_request_policies().create(org_id, input).await?)
+    }
+
+    /// Get a policy by its ID.
+    pub async fn get_by_id(&self, id: Uuid) -> DbResult<Option<OrgRequestPolicy>> {
+        self.db.org_request_policies().get_by_id(id).await
+    }
+
+    /// List all policies for an organization, highest priority first.
+    pub async fn list_by_org(&self, org_id: Uuid) -> DbResult<Vec<OrgRequestPolicy>> {
+        self.db.org_request_policies().list_by_org(org_id).await
+    }
+
+    /// List enabled policies for an organization, highest priority first.
+    pub async fn list_enabled_by_org(&self, org_id: Uuid) -> DbResult<Vec<OrgRequestPolicy>> {
+        self.db
+            .org_request_policies()
+            .list_enabled_by_org(org_id)
+            .await
+    }
+
+    /// Count policies for an organization.
+    pub async fn count_by_org(&self, org_id: Uuid) -> DbResult<i64> {
+        self.db.org_request_policies().count_by_org(org_id).await
+    }
+
+    /// Update a policy.
+    ///
+    /// The action/modifications pairing is checked against the policy as it
+    /// will be after the update, so switching a `modify` policy to `deny`
+    /// must also clear its modifications.
+    pub async fn update(
+        &self,
+        id: Uuid,
+        input: UpdateOrgRequestPolicy,
+    ) -> Result<OrgRequestPolicy, OrgRequestPolicyError> {
+        if let Some(ref condition) = input.condition {
+            self.validate_condition(condition)?;
+        }
+
+        let current = self
+            .get_by_id(id)
+            .await?
+            .ok_or(OrgRequestPolicyError::NotFound)?;
+        let action = input.action.unwrap_or(current.action);
+        let modifications = match &input.modifications {
+            Some(modifications) => modifications.as_ref(),
+            None => current.modifications.as_ref(),
+        };
+        validate_modifications(action, modifications)?;
+
+        Ok(self.db.org_request_policies().update(id, input).await?)
+    }
+
+    /// Delete a policy (hard delete).
+    pub async fn delete(&self, id: Uuid) -> DbResult<()> {
+        self.db.org_request_policies().delete(id).await
+    }
+
+    /// Run the checks `create` applies, without saving anything.
+    pub fn validate(&self, input: &CreateOrgRequestPolicy) -> Result<(), OrgRequestPolicyError> {
+        self.validate_condition(&input.condition)?;
+        validate_modifications(input.action, input.modifications.as_ref())
+    }
+
+    /// Validate a CEL condition without saving anything.
+    pub fn validate_condition(&self, condition: &str) -> Result<(), OrgRequestPolicyError> {
+        AuthzEngine::validate_expression_with_max_length(condition, self.max_expression_length)?;
+        Ok(())
+    }
+
+    /// Drop the org's cached policies so the next request sees the change.
+    ///
+    /// If request policies are disabled (no registry), this is a no-op.
+    pub async fn invalidate_cache(&self, org_id: Uuid, registry: Option<&RequestPolicyRegistry>) {
+        if let Some(registry) = registry {
+            registry.invalidate(org_id).await;
+        }
+    }
+}
+
+/// `modify` policies need a non-empty object of fields to set; other
+/// actions must not carry one.
+fn validate_modifications(
+    action: RequestPolicyAction,
+    modifications: Option<&serde_json::Value>,
+) -> Result<(), OrgRequestPolicyError> {
+    match (action, modifications) {
+        (RequestPolicyAction::Modify, Some(serde_json::Value::Object(map))) if !map.is_empty() => {
+            Ok(())
+        }
+        (RequestPolicyAction::Modify, _) => Err(OrgRequestPolicyError::InvalidModifications(
+            "modify policies require `modifications` to be a non-empty object".to_string(),
+        )),
+        (_, None) => Ok(()),
+        (action, Some(_)) => Err(OrgRequestPolicyError::InvalidModifications(format!(
+            "`modifications` only applies to modify policies, not {action}"
+        ))),
+    }
+}
+
+/// Errors that can occur during request policy operations.
+#[derive(Debug, Error)]
+pub enum OrgRequestPolicyError {
+    #[error("Database error: {0}")]
+    Database(#[from] crate::db::DbError),
+
+    #[error("Invalid CEL expression: {0}")]
+    InvalidCondition(String),
+
+    #[error("Invalid modifications: {0}")]
+    InvalidModifications(String),
+
+    #[error("Request policy not found")]
+    NotFound,
+}
+
+impl From<crate::authz::AuthzError> for OrgRequestPolicyError {
+    fn from(e: crate::authz::AuthzError) -> Self {
+        OrgRequestPolicyError::InvalidCondition(e.to_string())
+    }
+}
@@ -4,7 +4,7 @@ use uuid::Uuid;
 
 use crate::{
     db::{DbPool, DbResult},
-    models::{CreateOrgSsoConfig, OrgSsoConfig, SsoProviderType, UpdateOrgSsoConfig},
+    models::{CreateOrgSsoConfig, OrgSsoConfig, SsoProviderType, SsoRoleRule, UpdateOrgSsoConfig},
     secrets::SecretManager,
 };
 
@@ -361,6 +361,19 @@ impl OrgSsoConfigService {
         Ok(())
     }
 
+    /// Get the claim-based role rules for an organization.
+    pub async fn get_role_rules(&self, org_id: Uuid) -> DbResult<Vec<SsoRoleRule>> {
+        self.db.org_sso_configs().get_role_rules(org_id).await
+    }
+
+    /// Replace the claim-based role rules for an organization.
+    pub async fn set_role_rules(&self, org_id: Uuid, rules: &[SsoRoleRule]) -> DbResult<()> {
+        self.db
+            .org_sso_configs()
+            .set_role_rules(org_id, rules)
+            .await
+    }
+
     /// Find SSO configuration by email domain.
     ///
     /// Used for IdP discovery during login.
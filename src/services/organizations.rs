@@ -1,10 +1,13 @@
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
 use uuid::Uuid;
 
 use crate::{
     db::{DbPool, DbResult, ListParams, ListResult},
-    models::{CreateOrganization, Organization, UpdateOrganization},
+    models::{
+        CreateOrganization, ModelAccessPolicy, ModelDegradationPolicy, OrgEntitlements,
+        OrgNetworkPolicy, Organization, ParameterGovernance, UpdateOrganization,
+    },
 };
 
 /// Service layer for organization operations
@@ -48,6 +51,70 @@ impl OrganizationService {
         self.db.organizations().update(id, input).await
     }
 
+    /// Replace an organization's network policy (`None` lifts it)
+    pub async fn set_network_policy(
+        &self,
+        id: Uuid,
+        policy: Option<&OrgNetworkPolicy>,
+    ) -> DbResult<Organization> {
+        self.db.organizations().set_network_policy(id, policy).await
+    }
+
+    /// Replace an organization's parameter governance rules (`None` lifts them)
+    pub async fn set_parameter_governance(
+        &self,
+        id: Uuid,
+        governance: Option<&ParameterGovernance>,
+    ) -> DbResult<Organization> {
+        self.db
+            .organizations()
+            .set_parameter_governance(id, governance)
+            .await
+    }
+
+    /// Replace an organization's model access policy (`None` lifts it)
+    pub async fn set_model_access(
+        &self,
+        id: Uuid,
+        policy: Option<&ModelAccessPolicy>,
+    ) -> DbResult<Organization> {
+        self.db.organizations().set_model_access(id, policy).await
+    }
+
+    /// Replace an organization's model degradation policy (`None` opts it out)
+    pub async fn set_model_degradation(
+        &self,
+        id: Uuid,
+        policy: Option<&ModelDegradationPolicy>,
+    ) -> DbResult<Organization> {
+        self.db
+            .organizations()
+            .set_model_degradation(id, policy)
+            .await
+    }
+
+    /// Replace an organization's plan and feature overrides (`None` puts it
+    /// back on the default plan)
+    pub async fn set_entitlements(
+        &self,
+        id: Uuid,
+        entitlements: Option<&OrgEntitlements>,
+    ) -> DbResult<Organization> {
+        self.db
+            .organizations()
+            .set_entitlements(id, entitlements)
+            .await
+    }
+
+    /// Replace an organization's labels
+    pub async fn set_labels(
+        &self,
+        id: Uuid,
+        labels: &BTreeMap<String, String>,
+    ) -> DbResult<Organization> {
+        self.db.organizations().set_labels(id, labels).await
+    }
+
     /// Delete (soft-delete) an organization by ID
     pub async fn delete(&self, id: Uuid) -> DbResult<()> {
         self.db.organizations().delete(id).await
@@ -0,0 +1,99 @@
+//! Service layer for admin passkeys (WebAuthn credentials).
+//!
+//! The WebAuthn ceremonies themselves run in the `/auth/webauthn` routes;
+//! this service only stores and looks up the resulting credentials.
+
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    db::{DbPool, DbResult},
+    models::{AdminPasskey, CreateAdminPasskey, MAX_PASSKEY_NAME_LENGTH},
+};
+
+/// Name given to passkeys registered without one.
+const DEFAULT_PASSKEY_NAME: &str = "Passkey";
+
+/// Service layer for admin passkey storage.
+#[derive(Clone)]
+pub struct PasskeyService {
+    db: Arc<DbPool>,
+}
+
+impl PasskeyService {
+    pub fn new(db: Arc<DbPool>) -> Self {
+        Self { db }
+    }
+
+    /// List a user's passkeys, oldest first.
+    pub async fn list(&self, user_id: Uuid) -> DbResult<Vec<AdminPasskey>> {
+        self.db.admin_passkeys().list_by_user(user_id).await
+    }
+
+    /// Whether the user has registered at least one passkey.
+    pub async fn has_any(&self, user_id: Uuid) -> DbResult<bool> {
+        Ok(!self.list(user_id).await?.is_empty())
+    }
+
+    /// Look up a passkey by credential ID.
+    pub async fn get_by_credential_id(&self, credential_id: &str) -> DbResult<Option<AdminPasskey>> {
+        self.db
+            .admin_passkeys()
+            .get_by_credential_id(credential_id)
+            .await
+    }
+
+    /// Store a newly registered passkey. Blank names fall back to a default
+    /// and long names are truncated.
+    pub async fn register(
+        &self,
+        user_id: Uuid,
+        credential_id: String,
+        name: Option<&str>,
+        credential: String,
+    ) -> DbResult<AdminPasskey> {
+        self.db
+            .admin_passkeys()
+            .create(CreateAdminPasskey {
+                user_id,
+                credential_id,
+                name: normalize_name(name),
+                credential,
+            })
+            .await
+    }
+
+    /// Record a successful sign-in with the updated credential.
+    pub async fn record_use(&self, id: Uuid, credential: &str) -> DbResult<()> {
+        self.db.admin_passkeys().record_use(id, credential).await
+    }
+
+    /// Remove one of the user's passkeys.
+    pub async fn delete(&self, user_id: Uuid, id: Uuid) -> DbResult<bool> {
+        self.db.admin_passkeys().delete(user_id, id).await
+    }
+}
+
+fn normalize_name(name: Option<&str>) -> String {
+    match name.map(str::trim).filter(|n| !n.is_empty()) {
+        Some(name) => name.chars().take(MAX_PASSKEY_NAME_LENGTH).collect(),
+        None => DEFAULT_PASSKEY_NAME.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_name() {
+        assert_eq!(normalize_name(None), "Passkey");
+        assert_eq!(normalize_name(Some("   ")), "Passkey");
+        assert_eq!(normalize_name(Some(" YubiKey ")), "YubiKey");
+        assert_eq!(
+            normalize_name(Some(&"x".repeat(100))).chars().count(),
+            MAX_PASSKEY_NAME_LENGTH
+        );
+    }
+}
@@ -1,10 +1,10 @@
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
 use uuid::Uuid;
 
 use crate::{
     db::{DbPool, DbResult, ListParams, repos::ListResult},
-    models::{CreateProject, Project, UpdateProject},
+    models::{CreateProject, ModelAccessPolicy, ParameterGovernance, Project, UpdateProject},
 };
 
 /// Service layer for project operations
@@ -70,6 +70,36 @@ impl ProjectService {
         self.db.projects().update(id, input).await
     }
 
+    /// Replace a project's parameter governance rules (`None` lifts them)
+    pub async fn set_parameter_governance(
+        &self,
+        id: Uuid,
+        governance: Option<&ParameterGovernance>,
+    ) -> DbResult<Project> {
+        self.db
+            .projects()
+            .set_parameter_governance(id, governance)
+            .await
+    }
+
+    /// Replace a project's model access policy (`None` lifts it)
+    pub async fn set_model_access(
+        &self,
+        id: Uuid,
+        policy: Option<&ModelAccessPolicy>,
+    ) -> DbResult<Project> {
+        self.db.projects().set_model_access(id, policy).await
+    }
+
+    /// Replace a project's labels
+    pub async fn set_labels(
+        &self,
+        id: Uuid,
+        labels: &BTreeMap<String, String>,
+    ) -> DbResult<Project> {
+        self.db.projects().set_labels(id, labels).await
+    }
+
     /// Delete (soft-delete) a project by ID
     pub async fn delete(&self, id: Uuid) -> DbResult<()> {
         self.db.projects().delete(id).await
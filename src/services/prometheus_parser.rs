@@ -33,6 +33,12 @@ pub struct ParsedMetrics {
     pub output_tokens: HashMap<String, f64>,
     /// Cost in microcents by provider
     pub cost_microcents: HashMap<String, f64>,
+    /// Streaming time-to-first-chunk histograms by provider, summed across models
+    pub ttft_histograms: HashMap<String, HistogramData>,
+    /// Streaming inter-chunk latency histograms by provider, summed across models
+    pub inter_token_latency_histograms: HashMap<String, HistogramData>,
+    /// Streaming throughput (tokens/second) histograms by provider, summed across models
+    pub tokens_per_second_histograms: HashMap<String, HistogramData>,
 }
 
 /// Parse Prometheus text exposition format into structured metrics.
@@ -42,6 +48,9 @@ pub struct ParsedMetrics {
 /// - `llm_requests_total` counter for request/error counts
 /// - `llm_input_tokens_total`, `llm_output_tokens_total` for token usage
 /// - `llm_cost_microcents_total` for costs
+/// - `llm_streaming_time_to_first_chunk_seconds`,
+///   `llm_streaming_inter_token_latency_seconds` and
+///   `llm_streaming_output_tokens_per_second` histograms for streaming timings
 pub fn parse_prometheus_text(text: &str) -> ParsedMetrics {
     let mut metrics = ParsedMetrics::default();
 
@@ -139,10 +148,38 @@ pub fn parse_prometheus_text(text: &str) -> ParsedMetrics {
         {
             *metrics.cost_microcents.entry(provider.clone()).or_default() += cost;
         }
+        // Parse streaming timing histograms
+        else {
+            for (name, histograms) in [
+                (
+                    "llm_streaming_time_to_first_chunk_seconds",
+                    &mut metrics.ttft_histograms,
+                ),
+                (
+                    "llm_streaming_inter_token_latency_seconds",
+                    &mut metrics.inter_token_latency_histograms,
+                ),
+                (
+                    "llm_streaming_output_tokens_per_second",
+                    &mut metrics.tokens_per_second_histograms,
+                ),
+            ] {
+                if let Some(rest) = line.strip_prefix(name) {
+                    merge_histogram_sample(histograms, rest);
+                    break;
+                }
+            }
+        }
     }
 
     // Sort histogram buckets by upper bound
-    for histogram in metrics.latency_histograms.values_mut() {
+    for histogram in metrics
+        .latency_histograms
+        .values_mut()
+        .chain(metrics.ttft_histograms.values_mut())
+        .chain(metrics.inter_token_latency_histograms.values_mut())
+        .chain(metrics.tokens_per_second_histograms.values_mut())
+    {
         histogram
             .buckets
             .sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
@@ -171,6 +208,36 @@ fn parse_metric_line(rest: &str) -> Option<(HashMap<String, String>, &str)> {
     Some((labels, value_str))
 }
 
+/// Merge a `_bucket`, `_sum` or `_count` sample into the provider's histogram,
+/// summing series that differ only by other labels (e.g. model).
+fn merge_histogram_sample(histograms: &mut HashMap<String, HistogramData>, rest: &str) {
+    let Some((suffix, rest)) = rest.split_once('{') else {
+        return;
+    };
+    let Some((labels, value)) = parse_metric_line(rest) else {
+        return;
+    };
+    let (Some(provider), Ok(value)) = (labels.get("provider"), value.parse::<f64>()) else {
+        return;
+    };
+
+    match suffix {
+        "_bucket" => {
+            let Some(le) = labels.get("le").and_then(|le| parse_le(le).ok()) else {
+                return;
+            };
+            let histogram = histograms.entry(provider.clone()).or_default();
+            match histogram.buckets.iter_mut().find(|(bound, _)| *bound == le) {
+                Some((_, count)) => *count += value,
+                None => histogram.buckets.push((le, value)),
+            }
+        }
+        "_sum" => histograms.entry(provider.clone()).or_default().sum += value,
+        "_count" => histograms.entry(provider.clone()).or_default().count += value,
+        _ => {}
+    }
+}
+
 /// Split label string by commas, respecting quoted values.
 fn split_labels(s: &str) -> Vec<&str> {
     let mut parts = Vec::new();
@@ -328,6 +395,35 @@ llm_cost_microcents_total{provider="openai",model="gpt-4"} 150000
         assert_eq!(metrics.cost_microcents.get("openai"), Some(&150000.0));
     }
 
+    #[test]
+    fn test_parse_streaming_histograms() {
+        let text = r#"
+llm_streaming_time_to_first_chunk_seconds_bucket{provider="openai",model="gpt-4",le="0.5"} 3
+llm_streaming_time_to_first_chunk_seconds_bucket{provider="openai",model="gpt-4",le="+Inf"} 4
+llm_streaming_time_to_first_chunk_seconds_bucket{provider="openai",model="gpt-4o",le="0.5"} 1
+llm_streaming_time_to_first_chunk_seconds_bucket{provider="openai",model="gpt-4o",le="+Inf"} 1
+llm_streaming_time_to_first_chunk_seconds_sum{provider="openai",model="gpt-4"} 2.0
+llm_streaming_time_to_first_chunk_seconds_sum{provider="openai",model="gpt-4o"} 0.25
+llm_streaming_time_to_first_chunk_seconds_count{provider="openai",model="gpt-4"} 4
+llm_streaming_time_to_first_chunk_seconds_count{provider="openai",model="gpt-4o"} 1
+llm_streaming_output_tokens_per_second_sum{provider="openai",model="gpt-4"} 200
+llm_streaming_output_tokens_per_second_count{provider="openai",model="gpt-4"} 4
+"#;
+
+        let metrics = parse_prometheus_text(text);
+
+        // Series for both models are merged into one histogram per provider
+        let ttft = metrics.ttft_histograms.get("openai").unwrap();
+        assert_eq!(ttft.buckets, vec![(0.5, 4.0), (f64::INFINITY, 5.0)]);
+        assert_eq!(ttft.sum, 2.25);
+        assert_eq!(ttft.count, 5.0);
+
+        let throughput = metrics.tokens_per_second_histograms.get("openai").unwrap();
+        assert_eq!(average_from_histogram(throughput), Some(50.0));
+        assert!(metrics.inter_token_latency_histograms.is_empty());
+        assert!(metrics.latency_histograms.is_empty());
+    }
+
     #[test]
     fn test_percentile_from_histogram() {
         let histogram = HistogramData {
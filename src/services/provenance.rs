@@ -0,0 +1,306 @@
+//! Signed provenance metadata for generated text.
+//!
+//! With `[features.provenance]` enabled, successful non-streaming completion
+//! responses carry a [`ProvenanceMetadata`] block: the request ID, the model
+//! that served it, when it was signed, and a SHA-256 of the generated text,
+//! all covered by an HMAC-SHA256 signature with the gateway's key. Anyone
+//! holding the text and its metadata can later ask the gateway, through
+//! `/admin/v1/provenance/verify`, whether the pair was produced here.
+//!
+//! The metadata is returned in [`PROVENANCE_HEADER`] as base64url-encoded
+//! JSON, in a `provenance` field of the response body, or both. Streaming
+//! responses are not annotated, since the text isn't known when the headers
+//! are sent.
+
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+use crate::config::TransformEndpoint;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Response header with the base64url-encoded provenance metadata.
+pub const PROVENANCE_HEADER: &str = "X-Hadrian-Provenance";
+
+/// Response body field with the provenance metadata.
+pub const PROVENANCE_FIELD: &str = "provenance";
+
+/// Version of the signed fields; bumped if what is signed changes.
+pub const PROVENANCE_VERSION: u32 = 1;
+
+/// Signed metadata attesting that a text was generated through the gateway.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ProvenanceMetadata {
+    /// Version of the signed fields
+    pub version: u32,
+    /// ID of the request that generated the text
+    pub request_id: String,
+    /// Model that generated the text
+    pub model: String,
+    /// When the metadata was signed, as Unix seconds
+    pub created_at: i64,
+    /// Hex-encoded SHA-256 of the generated text
+    pub content_sha256: String,
+    /// Hex-encoded HMAC-SHA256 over the other fields
+    pub signature: String,
+}
+
+/// Why a text/metadata pair failed verification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum ProvenanceMismatch {
+    /// The metadata has a version this gateway doesn't sign.
+    #[error("unsupported provenance version")]
+    UnsupportedVersion,
+    /// The metadata wasn't signed with this gateway's key, or was altered.
+    #[error("signature does not match")]
+    InvalidSignature,
+    /// The metadata is genuine, but the text isn't what it was issued for.
+    #[error("text does not match the signed content hash")]
+    ContentMismatch,
+}
+
+impl ProvenanceMismatch {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::UnsupportedVersion => "unsupported_version",
+            Self::InvalidSignature => "invalid_signature",
+            Self::ContentMismatch => "content_mismatch",
+        }
+    }
+}
+
+impl ProvenanceMetadata {
+    /// Sign metadata for `text`, generated by `model` for `request_id`.
+    pub fn sign(key: &str, request_id: &str, model: &str, text: &str, now: DateTime<Utc>) -> Self {
+        let mut metadata = Self {
+            version: PROVENANCE_VERSION,
+            request_id: request_id.to_string(),
+            model: model.to_string(),
+            created_at: now.timestamp(),
+            content_sha256: content_hash(text),
+            signature: String::new(),
+        };
+        metadata.signature = hex::encode(metadata.mac(key));
+        metadata
+    }
+
+    /// Check that the metadata was signed with `key` for exactly `text`.
+    pub fn verify(&self, key: &str, text: &str) -> Result<(), ProvenanceMismatch> {
+        if self.version != PROVENANCE_VERSION {
+            return Err(ProvenanceMismatch::UnsupportedVersion);
+        }
+        let signature =
+            hex::decode(&self.signature).map_err(|_| ProvenanceMismatch::InvalidSignature)?;
+        if !bool::from(self.mac(key).as_slice().ct_eq(&signature)) {
+            return Err(ProvenanceMismatch::InvalidSignature);
+        }
+        if !bool::from(
+            content_hash(text)
+                .as_bytes()
+                .ct_eq(self.content_sha256.as_bytes()),
+        ) {
+            return Err(ProvenanceMismatch::ContentMismatch);
+        }
+        Ok(())
+    }
+
+    /// Encode for [`PROVENANCE_HEADER`].
+    pub fn to_header(&self) -> String {
+        let json = serde_json::to_vec(self).expect("provenance metadata serializes");
+        URL_SAFE_NO_PAD.encode(json)
+    }
+
+    /// Decode a [`PROVENANCE_HEADER`] value.
+    pub fn from_header(value: &str) -> Option<Self> {
+        let json = URL_SAFE_NO_PAD.decode(value.trim()).ok()?;
+        serde_json::from_slice(&json).ok()
+    }
+
+    /// HMAC over the signed fields, one per line so no field can bleed into
+    /// the next.
+    fn mac(&self, key: &str) -> Vec<u8> {
+        let mut mac =
+            HmacSha256::new_from_slice(key.as_bytes()).expect("HMAC-SHA256 accepts any key length");
+        mac.update(
+            format!(
+                "v{}\n{}\n{}\n{}\n{}",
+                self.version, self.request_id, self.model, self.created_at, self.content_sha256
+            )
+            .as_bytes(),
+        );
+        mac.finalize().into_bytes().to_vec()
+    }
+}
+
+fn content_hash(text: &str) -> String {
+    hex::encode(Sha256::digest(text.as_bytes()))
+}
+
+/// The generated text in a non-streaming response body, as signed.
+///
+/// - Chat completions: the first choice's message content, with text parts
+///   concatenated.
+/// - Completions: the first choice's text.
+/// - Responses: the `output_text` parts of all output messages,
+///   concatenated, like the SDKs' `output_text`.
+///
+/// `None` when the endpoint isn't a completion endpoint or the body has no
+/// text, such as a response consisting only of tool calls.
+pub fn output_text(endpoint: TransformEndpoint, body: &Value) -> Option<String> {
+    let text = match endpoint {
+        TransformEndpoint::ChatCompletions => match body.pointer("/choices/0/message/content")? {
+            Value::String(text) => text.clone(),
+            Value::Array(parts) => parts
+                .iter()
+                .filter(|p| p.get("type").and_then(Value::as_str) == Some("text"))
+                .filter_map(|p| p.get("text").and_then(Value::as_str))
+                .collect(),
+            _ => return None,
+        },
+        TransformEndpoint::Completions => body.pointer("/choices/0/text")?.as_str()?.to_string(),
+        TransformEndpoint::Responses => body
+            .get("output")?
+            .as_array()?
+            .iter()
+            .filter(|item| item.get("type").and_then(Value::as_str) == Some("message"))
+            .filter_map(|item| item.get("content").and_then(Value::as_array))
+            .flatten()
+            .filter(|part| part.get("type").and_then(Value::as_str) == Some("output_text"))
+            .filter_map(|part| part.get("text").and_then(Value::as_str))
+            .collect(),
+        TransformEndpoint::Embeddings => return None,
+    };
+    (!text.is_empty()).then_some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const KEY: &str = "0123456789abcdef0123456789abcdef";
+
+    fn signed(text: &str) -> ProvenanceMetadata {
+        ProvenanceMetadata::sign(KEY, "req-123", "openai/gpt-4o-mini", text, Utc::now())
+    }
+
+    #[test]
+    fn signed_metadata_verifies() {
+        let metadata = signed("The answer is 42.");
+        assert_eq!(metadata.version, PROVENANCE_VERSION);
+        assert_eq!(metadata.verify(KEY, "The answer is 42."), Ok(()));
+    }
+
+    #[test]
+    fn altered_text_or_metadata_is_rejected() {
+        let metadata = signed("The answer is 42.");
+        assert_eq!(
+            metadata.verify(KEY, "The answer is 43."),
+            Err(ProvenanceMismatch::ContentMismatch)
+        );
+
+        let other_model = ProvenanceMetadata {
+            model: "openai/gpt-4o".into(),
+            ..metadata.clone()
+        };
+        assert_eq!(
+            other_model.verify(KEY, "The answer is 42."),
+            Err(ProvenanceMismatch::InvalidSignature)
+        );
+
+        // Matching the hash isn't enough without the key
+        let forged = ProvenanceMetadata {
+            request_id: "req-456".into(),
+            ..metadata.clone()
+        };
+        assert_eq!(
+            forged.verify(KEY, "The answer is 42."),
+            Err(ProvenanceMismatch::InvalidSignature)
+        );
+        assert_eq!(
+            metadata.verify("fedcba9876543210fedcba9876543210", "The answer is 42."),
+            Err(ProvenanceMismatch::InvalidSignature)
+        );
+
+        let future = ProvenanceMetadata {
+            version: 2,
+            ..metadata
+        };
+        assert_eq!(
+            future.verify(KEY, "The answer is 42."),
+            Err(ProvenanceMismatch::UnsupportedVersion)
+        );
+    }
+
+    #[test]
+    fn header_round_trips() {
+        let metadata = signed("Hello");
+        let header = metadata.to_header();
+        assert!(!header.contains('='));
+        assert_eq!(ProvenanceMetadata::from_header(&header), Some(metadata));
+        assert_eq!(ProvenanceMetadata::from_header("not base64!"), None);
+    }
+
+    #[test]
+    fn output_text_per_endpoint() {
+        let chat = json!({
+            "choices": [
+                {"message": {"role": "assistant", "content": "first"}},
+                {"message": {"role": "assistant", "content": "second"}}
+            ]
+        });
+        assert_eq!(
+            output_text(TransformEndpoint::ChatCompletions, &chat).as_deref(),
+            Some("first")
+        );
+        let parts = json!({
+            "choices": [{"message": {"content": [
+                {"type": "text", "text": "Hello, "},
+                {"type": "image_url", "image_url": {"url": "https://example.com/a.png"}},
+                {"type": "text", "text": "world"}
+            ]}}]
+        });
+        assert_eq!(
+            output_text(TransformEndpoint::ChatCompletions, &parts).as_deref(),
+            Some("Hello, world")
+        );
+        let tool_calls = json!({"choices": [{"message": {"content": null, "tool_calls": []}}]});
+        assert_eq!(
+            output_text(TransformEndpoint::ChatCompletions, &tool_calls),
+            None
+        );
+
+        let completion = json!({"choices": [{"text": "Once upon a time"}]});
+        assert_eq!(
+            output_text(TransformEndpoint::Completions, &completion).as_deref(),
+            Some("Once upon a time")
+        );
+
+        let response = json!({
+            "output": [
+                {"type": "reasoning", "summary": []},
+                {"type": "message", "content": [{"type": "output_text", "text": "Hello"}]},
+                {"type": "function_call", "name": "lookup", "arguments": "{}"},
+                {"type": "message", "content": [
+                    {"type": "refusal", "refusal": "no"},
+                    {"type": "output_text", "text": " again"}
+                ]}
+            ]
+        });
+        assert_eq!(
+            output_text(TransformEndpoint::Responses, &response).as_deref(),
+            Some("Hello again")
+        );
+        assert_eq!(
+            output_text(TransformEndpoint::Embeddings, &json!({"data": []})),
+            None
+        );
+    }
+}
@@ -0,0 +1,141 @@
+//! Copies of gateway files stored at providers.
+//!
+//! Some provider endpoints only take the provider's own file IDs. A file
+//! uploaded to the gateway is copied to a provider when a client asks for it
+//! with the upload's `provider` field, or when a fine-tuning job first uses
+//! it, and the copy is recorded in `provider_files` so later requests reuse
+//! it. Deleting a gateway file deletes its copies.
+
+use std::sync::Arc;
+
+use bytes::Bytes;
+use uuid::Uuid;
+
+use crate::{
+    AppState,
+    config::ProviderConfig,
+    db::{DbError, DbPool},
+    models::File,
+    providers::{Provider, ProviderError},
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum ProviderFileError {
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+    #[error(transparent)]
+    Db(#[from] DbError),
+}
+
+/// Instantiate a provider from the gateway config, with its runtime settings
+/// applied. Provider files and fine-tuning jobs outlive the request that
+/// created them and are managed without the caller's credentials, so they
+/// can only use configured providers.
+pub fn configured_provider(
+    state: &AppState,
+    provider_name: &str,
+) -> Result<(ProviderConfig, Arc<dyn Provider>), ProviderError> {
+    let config = state
+        .config
+        .providers
+        .get(provider_name)
+        .and_then(|config| state.provider_settings.apply(provider_name, config))
+        .ok_or_else(|| {
+            ProviderError::Internal(format!("provider '{provider_name}' is not available"))
+        })?;
+    let provider =
+        crate::init::create_provider_instance(&config, provider_name, &state.circuit_breakers)
+            .map_err(ProviderError::Internal)?;
+    Ok((config, provider))
+}
+
+/// Copy a file to a provider, returning the provider's file ID. If a
+/// concurrent request copied it first, the new copy is deleted and the
+/// existing one's ID returned.
+pub async fn upload(
+    state: &AppState,
+    db: &DbPool,
+    file: &File,
+    content: Bytes,
+    provider_name: &str,
+    provider: &dyn Provider,
+) -> Result<String, ProviderFileError> {
+    let provider_file_id = provider
+        .upload_file(
+            &state.http_client,
+            content,
+            file.filename.clone(),
+            file.purpose.as_str(),
+        )
+        .await?;
+
+    match db
+        .provider_files()
+        .create(file.id, provider_name, &provider_file_id)
+        .await
+    {
+        Ok(_) => Ok(provider_file_id),
+        Err(DbError::Conflict(_)) => {
+            delete_provider_copy(state, provider, provider_name, &provider_file_id).await;
+            let existing = db
+                .provider_files()
+                .get(file.id, provider_name)
+                .await?
+                .ok_or(DbError::NotFound)?;
+            Ok(existing.provider_file_id)
+        }
+        Err(e) => {
+            delete_provider_copy(state, provider, provider_name, &provider_file_id).await;
+            Err(e.into())
+        }
+    }
+}
+
+/// Delete a file's copies from their providers and forget them. Provider
+/// deletions are best effort: a copy the provider fails to delete is logged
+/// and forgotten anyway, so the gateway file can still be deleted.
+pub async fn delete_copies(state: &AppState, db: &DbPool, file_id: Uuid) -> Result<(), DbError> {
+    for copy in db.provider_files().list_by_file(file_id).await? {
+        match configured_provider(state, &copy.provider) {
+            Ok((_, provider)) => {
+                delete_provider_copy(
+                    state,
+                    provider.as_ref(),
+                    &copy.provider,
+                    &copy.provider_file_id,
+                )
+                .await
+            }
+            Err(e) => tracing::warn!(
+                error = %e,
+                provider = %copy.provider,
+                provider_file_id = %copy.provider_file_id,
+                "Provider file left behind: provider is no longer configured"
+            ),
+        }
+        match db.provider_files().delete(copy.id).await {
+            Ok(()) | Err(DbError::NotFound) => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+async fn delete_provider_copy(
+    state: &AppState,
+    provider: &dyn Provider,
+    provider_name: &str,
+    provider_file_id: &str,
+) {
+    if let Err(e) = provider
+        .delete_file(&state.http_client, provider_file_id)
+        .await
+    {
+        tracing::warn!(
+            error = %e,
+            provider = provider_name,
+            provider_file_id,
+            "Failed to delete provider file"
+        );
+    }
+}
@@ -75,6 +75,22 @@ pub struct ProviderStats {
     /// Total cost in microcents
     pub total_cost_microcents: i64,
 
+    /// 50th percentile streaming time to first token in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub p50_ttft_ms: Option<f64>,
+
+    /// 95th percentile streaming time to first token in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub p95_ttft_ms: Option<f64>,
+
+    /// Average gap between streamed chunks in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_inter_token_latency_ms: Option<f64>,
+
+    /// Average streaming output throughput in tokens per second
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_output_tokens_per_second: Option<f64>,
+
     /// When these stats were last updated
     pub last_updated: DateTime<Utc>,
 }
@@ -94,6 +110,10 @@ impl ProviderStats {
             input_tokens: 0,
             output_tokens: 0,
             total_cost_microcents: 0,
+            p50_ttft_ms: None,
+            p95_ttft_ms: None,
+            avg_inter_token_latency_ms: None,
+            avg_output_tokens_per_second: None,
             last_updated: Utc::now(),
         }
     }
@@ -202,6 +222,11 @@ pub struct ProviderStatsHistorical {
     /// stats require Prometheus to be configured.
     #[serde(default)]
     pub prometheus_configured: bool,
+
+    /// Scheduled probe results per bucket and model, when probes are
+    /// configured for the provider. Available without Prometheus.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub probes: Vec<crate::models::ProbeBucketStats>,
 }
 
 /// Provider metrics service.
@@ -349,6 +374,14 @@ impl ProviderMetricsService {
             "sum by (provider) (llm_output_tokens_total)",
             // Cost
             "sum by (provider) (llm_cost_microcents_total)",
+            // P50 time to first token
+            "histogram_quantile(0.50, sum by (provider, le) (rate(llm_streaming_time_to_first_chunk_seconds_bucket[5m])))",
+            // P95 time to first token
+            "histogram_quantile(0.95, sum by (provider, le) (rate(llm_streaming_time_to_first_chunk_seconds_bucket[5m])))",
+            // Average inter-token latency
+            "sum by (provider) (rate(llm_streaming_inter_token_latency_seconds_sum[5m])) / sum by (provider) (rate(llm_streaming_inter_token_latency_seconds_count[5m]))",
+            // Average streaming throughput
+            "sum by (provider) (rate(llm_streaming_output_tokens_per_second_sum[5m])) / sum by (provider) (rate(llm_streaming_output_tokens_per_second_count[5m]))",
         ];
 
         let results = client.query_many(&queries).await?;
@@ -444,6 +477,46 @@ impl ProviderMetricsService {
             }
         }
 
+        // Process P50 time to first token
+        for mv in &results[9].values {
+            if let Some(provider) = mv.labels.get("provider")
+                && let Some(stats) = stats_map.get_mut(provider)
+                && mv.value.is_finite()
+            {
+                stats.p50_ttft_ms = Some(mv.value * 1000.0);
+            }
+        }
+
+        // Process P95 time to first token
+        for mv in &results[10].values {
+            if let Some(provider) = mv.labels.get("provider")
+                && let Some(stats) = stats_map.get_mut(provider)
+                && mv.value.is_finite()
+            {
+                stats.p95_ttft_ms = Some(mv.value * 1000.0);
+            }
+        }
+
+        // Process average inter-token latency
+        for mv in &results[11].values {
+            if let Some(provider) = mv.labels.get("provider")
+                && let Some(stats) = stats_map.get_mut(provider)
+                && mv.value.is_finite()
+            {
+                stats.avg_inter_token_latency_ms = Some(mv.value * 1000.0);
+            }
+        }
+
+        // Process average streaming throughput
+        for mv in &results[12].values {
+            if let Some(provider) = mv.labels.get("provider")
+                && let Some(stats) = stats_map.get_mut(provider)
+                && mv.value.is_finite()
+            {
+                stats.avg_output_tokens_per_second = Some(mv.value);
+            }
+        }
+
         // Fetch errors by status code
         let errors_query =
             "sum by (provider, status_code) (llm_requests_total{status_code=~\"4..|5..\"})";
@@ -531,6 +604,19 @@ impl ProviderMetricsService {
                 stats.total_cost_microcents = cost as i64;
             }
 
+            // Streaming timings
+            if let Some(histogram) = parsed.ttft_histograms.get(provider) {
+                stats.p50_ttft_ms = percentile_from_histogram(histogram, 0.50).map(|v| v * 1000.0);
+                stats.p95_ttft_ms = percentile_from_histogram(histogram, 0.95).map(|v| v * 1000.0);
+            }
+            if let Some(histogram) = parsed.inter_token_latency_histograms.get(provider) {
+                stats.avg_inter_token_latency_ms =
+                    average_from_histogram(histogram).map(|v| v * 1000.0);
+            }
+            if let Some(histogram) = parsed.tokens_per_second_histograms.get(provider) {
+                stats.avg_output_tokens_per_second = average_from_histogram(histogram);
+            }
+
             stats_map.insert(provider.clone(), stats);
         }
 
@@ -644,6 +730,7 @@ impl ProviderMetricsService {
             granularity,
             data,
             prometheus_configured: true,
+            probes: vec![],
         })
     }
 }
@@ -700,6 +787,16 @@ llm_request_duration_seconds_count{provider="openai",model="gpt-4"} 105
 llm_input_tokens_total{provider="openai",model="gpt-4"} 10000
 llm_output_tokens_total{provider="openai",model="gpt-4"} 5000
 llm_cost_microcents_total{provider="openai",model="gpt-4"} 150000
+
+llm_streaming_time_to_first_chunk_seconds_bucket{provider="openai",model="gpt-4",le="0.25"} 40
+llm_streaming_time_to_first_chunk_seconds_bucket{provider="openai",model="gpt-4",le="0.5"} 80
+llm_streaming_time_to_first_chunk_seconds_bucket{provider="openai",model="gpt-4",le="+Inf"} 80
+llm_streaming_time_to_first_chunk_seconds_sum{provider="openai",model="gpt-4"} 20
+llm_streaming_time_to_first_chunk_seconds_count{provider="openai",model="gpt-4"} 80
+llm_streaming_inter_token_latency_seconds_sum{provider="openai",model="gpt-4"} 30
+llm_streaming_inter_token_latency_seconds_count{provider="openai",model="gpt-4"} 1000
+llm_streaming_output_tokens_per_second_sum{provider="openai",model="gpt-4"} 3200
+llm_streaming_output_tokens_per_second_count{provider="openai",model="gpt-4"} 80
 "#;
 
         let service =
@@ -724,5 +821,11 @@ llm_cost_microcents_total{provider="openai",model="gpt-4"} 150000
 
         // Check errors by status
         assert_eq!(openai_stats.errors_by_status.get(&500), Some(&5));
+
+        // Check streaming timings
+        assert_eq!(openai_stats.p50_ttft_ms, Some(250.0));
+        assert!(openai_stats.p95_ttft_ms.unwrap() > 250.0);
+        assert!((openai_stats.avg_inter_token_latency_ms.unwrap() - 30.0).abs() < 1e-9);
+        assert_eq!(openai_stats.avg_output_tokens_per_second, Some(40.0));
     }
 }
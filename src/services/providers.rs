@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
 use uuid::Uuid;
 
@@ -413,6 +413,15 @@ impl DynamicProviderService {
             .await
     }
 
+    /// Replace a provider's labels
+    pub async fn set_labels(
+        &self,
+        id: Uuid,
+        labels: &BTreeMap<String, String>,
+    ) -> DbResult<DynamicProvider> {
+        self.db.providers().set_labels(id, labels).await
+    }
+
     /// Delete a provider, cleaning up the secret from the secrets manager.
     pub async fn delete(
         &self,
@@ -0,0 +1,502 @@
+//! Replay of stored Responses API requests.
+//!
+//! Every `POST /v1/responses` with persistence enabled leaves a row holding
+//! the request payload and the final `output`/`usage`. Replaying re-routes
+//! that payload (optionally to a different model or provider) and compares
+//! the new output with the stored one, which is the quickest way to tell
+//! whether an incident or regression follows the request or the provider.
+//!
+//! A replay is a single upstream call: no fallback chain, no streaming, no
+//! server-executed tool loop and no persistence. Tool calls the model emits
+//! are reported in the output text but never run, so replays have no side
+//! effects beyond the provider's own billing.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::{
+    AppState,
+    api_types::{CreateResponsesPayload, responses::ResponsesInput},
+    db::repos::ResponseRecord,
+    providers::Tenant,
+    routes::execution::{ResponsesExecutor, execute_provider},
+    routing::{resolver, route_model_extended},
+    services::{ResponsesStore, responses_chain},
+};
+
+/// Upper bound on the replayed response body. Matches the compactor's
+/// summarisation read limit; replays only need the final JSON document.
+const MAX_REPLAY_BODY_BYTES: usize = 8 * 1024 * 1024;
+
+/// Line diffs are quadratic; above this many line pairs the diff degrades to
+/// "everything removed, everything added" instead of running LCS.
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+#[derive(Debug, Error)]
+pub enum ReplayError {
+    #[error("stored request cannot be replayed: {0}")]
+    NotReplayable(String),
+    #[error("model routing failed: {0}")]
+    Routing(String),
+    #[error("provider execution failed: {0}")]
+    Execution(String),
+}
+
+/// Whether the replay calls the provider.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ReplayMode {
+    /// Resolve the target and build the payload without calling the provider
+    #[default]
+    DryRun,
+    /// Send the payload upstream and diff the result against the stored output
+    Live,
+}
+
+/// Overrides applied to the stored request before it is replayed.
+#[derive(Debug, Clone, Default)]
+pub struct ReplayOptions {
+    /// Model to replay against. Defaults to the model that served the
+    /// original request.
+    pub model: Option<String>,
+    /// Provider to replay against. Defaults to the provider that served the
+    /// original request (or normal routing when that isn't recorded).
+    pub provider: Option<String>,
+    pub mode: ReplayMode,
+}
+
+/// Where a replay was (or would be) sent.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ReplayTarget {
+    pub provider: String,
+    pub model: String,
+    /// "static" for config-defined providers, "dynamic" for DB-defined ones
+    pub source: String,
+}
+
+/// One side of the comparison: the stored response or the replayed one.
+#[derive(Debug, Clone, Default, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ReplayResult {
+    /// Provider that produced this output
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    /// Model that produced this output
+    pub model: String,
+    /// HTTP status returned upstream (replays only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_code: Option<u16>,
+    /// Output flattened to text: message content, then one line per tool call
+    pub text: String,
+    pub input_tokens: Option<i64>,
+    pub output_tokens: Option<i64>,
+    /// Wall-clock latency of the upstream call (replays only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    /// Error object reported by the provider, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<Value>,
+}
+
+/// Kind of a line in a [`ReplayDiff`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum DiffOp {
+    Equal,
+    /// Present only in the replay
+    Insert,
+    /// Present only in the original
+    Delete,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct DiffLine {
+    pub op: DiffOp,
+    pub text: String,
+}
+
+/// Line-level comparison of the original and replayed output text.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ReplayDiff {
+    pub identical: bool,
+    /// Share of lines common to both outputs, from 0.0 (disjoint) to 1.0
+    pub similarity: f64,
+    /// Replay tokens minus original tokens (when both are known)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_tokens_delta: Option<i64>,
+    pub lines: Vec<DiffLine>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ReplayOutcome {
+    pub response_id: String,
+    pub mode: ReplayMode,
+    pub target: ReplayTarget,
+    /// The payload sent (or, in dry-run mode, that would be sent) upstream
+    pub payload: Value,
+    pub original: ReplayResult,
+    /// Present in live mode only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replay: Option<ReplayResult>,
+    /// Present in live mode only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<ReplayDiff>,
+}
+
+/// Replay a stored response.
+///
+/// The caller is responsible for loading `record` within the right org and
+/// authorizing the replay.
+pub async fn replay_response(
+    state: &AppState,
+    store: &ResponsesStore,
+    record: &ResponseRecord,
+    options: ReplayOptions,
+) -> Result<ReplayOutcome, ReplayError> {
+    let mut payload: CreateResponsesPayload =
+        serde_json::from_value(record.request_payload.clone())
+            .map_err(|e| ReplayError::NotReplayable(format!("invalid request_payload: {e}")))?;
+
+    // Inline history the same way the foreground handler does, so the
+    // replay sees the conversation the original request saw
+    if let Some(prev_id) = payload.previous_response_id.take() {
+        let items = responses_chain::reconstruct_input(
+            store,
+            record.org_id,
+            &prev_id,
+            payload.input.take(),
+        )
+        .await
+        .map_err(|e| ReplayError::NotReplayable(format!("previous_response_id: {e}")))?;
+        payload.input = Some(ResponsesInput::Items(items));
+    }
+
+    let model_str = target_model_string(record, &payload, &options);
+    let routed = route_model_extended(Some(&model_str), &state.config.providers)
+        .map_err(|e| ReplayError::Routing(e.to_string()))?;
+    let resolved = resolver::resolve_to_provider(
+        routed,
+        state.db.as_ref(),
+        state.cache.as_ref(),
+        state.secrets.as_ref(),
+        &state.provider_settings,
+        None,
+    )
+    .await
+    .map_err(|e| ReplayError::Routing(e.to_string()))?;
+
+    payload.model = Some(resolved.model.clone());
+    payload.models = None;
+    payload.stream = false;
+    payload.background = None;
+    payload.store = Some(false);
+
+    let target = ReplayTarget {
+        provider: resolved.provider_name.clone(),
+        model: resolved.model.clone(),
+        source: resolved.source.to_string(),
+    };
+    let original = ReplayResult {
+        provider: record.provider.clone(),
+        model: record.model.clone(),
+        text: output_text(record.output.as_ref()),
+        input_tokens: usage_tokens(record.usage.as_ref(), "input_tokens"),
+        output_tokens: usage_tokens(record.usage.as_ref(), "output_tokens"),
+        error: record.error.clone(),
+        ..Default::default()
+    };
+    let payload_json = serde_json::to_value(&payload).unwrap_or(Value::Null);
+
+    if options.mode == ReplayMode::DryRun {
+        return Ok(ReplayOutcome {
+            response_id: record.id.clone(),
+            mode: options.mode,
+            target,
+            payload: payload_json,
+            original,
+            replay: None,
+            diff: None,
+        });
+    }
+
+    let started = Instant::now();
+    let response = execute_provider::<ResponsesExecutor>(
+        state,
+        &resolved.provider_name,
+        &resolved.provider_config,
+        payload,
+        &Tenant {
+            org_id: Some(record.org_id),
+            project_id: record.project_id,
+            ..Default::default()
+        },
+    )
+    .await
+    .map_err(|e| ReplayError::Execution(format!("{e:?}")))?;
+    let status = response.status().as_u16();
+    let bytes = axum::body::to_bytes(response.into_body(), MAX_REPLAY_BODY_BYTES)
+        .await
+        .map_err(|e| ReplayError::Execution(format!("failed to read body: {e}")))?;
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    let body: Value = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    let replay = ReplayResult {
+        provider: Some(target.provider.clone()),
+        model: target.model.clone(),
+        status_code: Some(status),
+        text: output_text(body.get("output")),
+        input_tokens: usage_tokens(body.get("usage"), "input_tokens"),
+        output_tokens: usage_tokens(body.get("usage"), "output_tokens"),
+        latency_ms: Some(latency_ms),
+        error: body.get("error").filter(|e| !e.is_null()).cloned(),
+    };
+    let diff = compare(&original, &replay);
+
+    Ok(ReplayOutcome {
+        response_id: record.id.clone(),
+        mode: options.mode,
+        target,
+        payload: payload_json,
+        original,
+        replay: Some(replay),
+        diff: Some(diff),
+    })
+}
+
+/// Build the model string to route. An explicit provider pins the static
+/// provider of that name; otherwise an explicit model routes normally, and
+/// with no overrides the original provider/model pair is reused.
+fn target_model_string(
+    record: &ResponseRecord,
+    payload: &CreateResponsesPayload,
+    options: &ReplayOptions,
+) -> String {
+    let model = options
+        .model
+        .clone()
+        .unwrap_or_else(|| record.model.clone());
+    match (&options.provider, &options.model, &record.provider) {
+        (Some(provider), _, _) => format!("{provider}/{model}"),
+        (None, Some(model), _) => model.clone(),
+        (None, None, Some(provider)) => format!("{provider}/{model}"),
+        (None, None, None) => payload.model.clone().unwrap_or(model),
+    }
+}
+
+/// Flatten a Responses API `output` array to comparable text: assistant
+/// message text first, then one `tool_call` line per function call.
+fn output_text(output: Option<&Value>) -> String {
+    let Some(items) = output.and_then(Value::as_array) else {
+        return String::new();
+    };
+    let mut lines = Vec::new();
+    for item in items {
+        match item.get("type").and_then(Value::as_str) {
+            Some("message") => {
+                for part in item
+                    .get("content")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                {
+                    if let Some(text) = part.get("text").and_then(Value::as_str) {
+                        lines.push(text.to_string());
+                    }
+                }
+            }
+            Some("function_call") => {
+                let name = item.get("name").and_then(Value::as_str).unwrap_or("");
+                let args = item.get("arguments").and_then(Value::as_str).unwrap_or("");
+                lines.push(format!("tool_call: {name} {args}"));
+            }
+            _ => {}
+        }
+    }
+    lines.join("\n")
+}
+
+fn usage_tokens(usage: Option<&Value>, field: &str) -> Option<i64> {
+    usage?.get(field)?.as_i64()
+}
+
+fn compare(original: &ReplayResult, replay: &ReplayResult) -> ReplayDiff {
+    let lines = diff_lines(&original.text, &replay.text);
+    ReplayDiff {
+        identical: original.text == replay.text,
+        similarity: similarity(&lines),
+        output_tokens_delta: replay
+            .output_tokens
+            .zip(original.output_tokens)
+            .map(|(r, o)| r - o),
+        lines,
+    }
+}
+
+/// Share of lines common to both sides of a diff, from 0.0 to 1.0.
+pub(crate) fn similarity(lines: &[DiffLine]) -> f64 {
+    let equal = lines.iter().filter(|l| l.op == DiffOp::Equal).count();
+    // Every equal line appears on both sides
+    let total = lines.len() + equal;
+    if total == 0 {
+        1.0
+    } else {
+        (2 * equal) as f64 / total as f64
+    }
+}
+
+/// Longest-common-subsequence line diff.
+pub(crate) fn diff_lines(original: &str, replay: &str) -> Vec<DiffLine> {
+    let a: Vec<&str> = original.lines().collect();
+    let b: Vec<&str> = replay.lines().collect();
+    let line = |op, text: &str| DiffLine {
+        op,
+        text: text.to_string(),
+    };
+
+    if a.len().saturating_mul(b.len()) > MAX_DIFF_CELLS {
+        return a
+            .iter()
+            .map(|t| line(DiffOp::Delete, t))
+            .chain(b.iter().map(|t| line(DiffOp::Insert, t)))
+            .collect();
+    }
+
+    // lcs[i][j] = LCS length of a[i..] and b[j..]
+    let mut lcs = vec![vec![0u32; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut out = Vec::with_capacity(a.len().max(b.len()));
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            out.push(line(DiffOp::Equal, a[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            out.push(line(DiffOp::Delete, a[i]));
+            i += 1;
+        } else {
+            out.push(line(DiffOp::Insert, b[j]));
+            j += 1;
+        }
+    }
+    out.extend(a[i..].iter().map(|t| line(DiffOp::Delete, t)));
+    out.extend(b[j..].iter().map(|t| line(DiffOp::Insert, t)));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn ops(lines: &[DiffLine]) -> Vec<(DiffOp, &str)> {
+        lines.iter().map(|l| (l.op, l.text.as_str())).collect()
+    }
+
+    #[test]
+    fn test_diff_lines_identical() {
+        let lines = diff_lines("a\nb", "a\nb");
+        assert_eq!(
+            ops(&lines),
+            vec![(DiffOp::Equal, "a"), (DiffOp::Equal, "b")]
+        );
+    }
+
+    #[test]
+    fn test_diff_lines_changed_middle() {
+        let lines = diff_lines("a\nb\nc", "a\nx\nc");
+        assert_eq!(
+            ops(&lines),
+            vec![
+                (DiffOp::Equal, "a"),
+                (DiffOp::Delete, "b"),
+                (DiffOp::Insert, "x"),
+                (DiffOp::Equal, "c"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_lines_empty_sides() {
+        assert!(diff_lines("", "").is_empty());
+        assert_eq!(ops(&diff_lines("", "a")), vec![(DiffOp::Insert, "a")]);
+        assert_eq!(ops(&diff_lines("a", "")), vec![(DiffOp::Delete, "a")]);
+    }
+
+    #[test]
+    fn test_compare_similarity_and_token_delta() {
+        let original = ReplayResult {
+            text: "a\nb".into(),
+            output_tokens: Some(10),
+            ..Default::default()
+        };
+        let replay = ReplayResult {
+            text: "a\nc".into(),
+            output_tokens: Some(14),
+            ..Default::default()
+        };
+        let diff = compare(&original, &replay);
+        assert!(!diff.identical);
+        assert!((diff.similarity - 0.5).abs() < f64::EPSILON);
+        assert_eq!(diff.output_tokens_delta, Some(4));
+
+        let same = compare(&original, &original);
+        assert!(same.identical);
+        assert!((same.similarity - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_output_text_messages_and_tool_calls() {
+        let output = json!([
+            {"type": "reasoning", "summary": []},
+            {"type": "message", "content": [
+                {"type": "output_text", "text": "Hello"},
+                {"type": "output_text", "text": "World"}
+            ]},
+            {"type": "function_call", "name": "lookup", "arguments": "{\"q\":1}"}
+        ]);
+        assert_eq!(
+            output_text(Some(&output)),
+            "Hello\nWorld\ntool_call: lookup {\"q\":1}"
+        );
+        assert_eq!(output_text(None), "");
+    }
+
+    #[test]
+    fn test_usage_tokens() {
+        let usage = json!({"input_tokens": 12, "output_tokens": 3});
+        assert_eq!(usage_tokens(Some(&usage), "input_tokens"), Some(12));
+        assert_eq!(usage_tokens(Some(&usage), "missing"), None);
+        assert_eq!(usage_tokens(None, "input_tokens"), None);
+    }
+
+    #[test]
+    fn test_replay_mode_defaults_to_dry_run() {
+        assert_eq!(ReplayMode::default(), ReplayMode::DryRun);
+        assert_eq!(
+            serde_json::from_value::<ReplayMode>(json!("live")).unwrap(),
+            ReplayMode::Live
+        );
+    }
+}
@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        DbPool, DbResult,
+        repos::{ListParams, ListResult},
+    },
+    models::{ReportRun, ReportRunFilter},
+};
+
+/// Service layer for scheduled report run history
+#[derive(Clone)]
+pub struct ReportRunService {
+    db: Arc<DbPool>,
+}
+
+impl ReportRunService {
+    pub fn new(db: Arc<DbPool>) -> Self {
+        Self { db }
+    }
+
+    /// Get a report run by ID, including the rendered report body
+    pub async fn get_by_id(&self, id: Uuid) -> DbResult<Option<ReportRun>> {
+        self.db.scheduled_report_runs().get_by_id(id).await
+    }
+
+    /// List report runs, newest first. Rendered bodies are omitted.
+    pub async fn list(
+        &self,
+        filter: ReportRunFilter,
+        params: ListParams,
+    ) -> DbResult<ListResult<ReportRun>> {
+        self.db.scheduled_report_runs().list(filter, params).await
+    }
+}
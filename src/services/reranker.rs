@@ -827,6 +827,8 @@ mod tests {
             file_id: Uuid::new_v4(),
             chunk_index: 0,
             content: content.to_string(),
+            char_start: 0,
+            char_end: 0,
             score,
             filename: Some("test.txt".to_string()),
             metadata: None,
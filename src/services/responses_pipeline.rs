@@ -20,25 +20,28 @@ use crate::{
     AppState,
     api_types::CreateResponsesPayload,
     auth::AuthenticatedRequest,
-    config::ProviderConfig,
+    config::{HttpToolConfig, ProviderConfig},
     db::repos::ResponseOwner,
     models::{
         ApiKeyOwner, SKILL_MAIN_FILE, SkillId, SkillRef, VersionSelector, validate_skill_name,
     },
+    providers::Tenant,
     routes::{
         api::wrap_streaming_with_guardrails,
-        execution::{ProviderExecutor, ResponsesExecutor},
+        execution::{ResponsesExecutor, execute_provider},
     },
     runtimes::{MountedFile, SkillMount},
     services::{
         FileSearchAuthContext, FileSearchContext, WebSearchContext,
         container_session::ContainerPersistence,
         file_search_tool::FileSearchExecutor,
+        http_tool::{HttpToolAudit, HttpToolExecutor},
         input_file_staging::StagedFile,
         server_tools::{ProviderCallback, ServerExecutedTool, ToolLoopRunner},
         shell_tool::ShellExecutor,
         web_search_tool::WebSearchExecutor,
     },
+    validation::url::UrlValidationOptions,
 };
 
 /// Identity fields used by the shell tool's usage attribution.
@@ -452,7 +455,7 @@ fn resolve_inline_skill(
 
 /// Wrap a streaming Responses-API response with the full server-side
 /// pipeline: output guardrails, the server-executed tool loop
-/// (`file_search` / `web_search` / `shell`), and the persister.
+/// (`file_search` / `web_search` / `shell` / HTTP tools), and the persister.
 ///
 /// Wrap order matches the foreground handler: guardrails first so the
 /// tool loop and persister only ever see content that has passed the
@@ -467,6 +470,10 @@ fn resolve_inline_skill(
 /// this (they pre-created the row); foreground also supplies it when
 /// `store=true`. Carries the row's `org_id` so persistence writes are
 /// tenant-scoped — a stale or wrong id can't punch into another org.
+///
+/// `max_iterations`: caller-specific cap on tool-loop turns (agent runs
+/// pass the agent's own limit). It can only lower
+/// `[features.server_tools] max_iterations`, never raise it.
 #[allow(clippy::too_many_arguments)] // each arg is load-bearing; bundling adds no clarity
 pub fn apply_streaming_pipeline(
     state: &AppState,
@@ -477,6 +484,9 @@ pub fn apply_streaming_pipeline(
     principal: PipelinePrincipal,
     mounted_skills: Vec<SkillMount>,
     staged_input_files: Vec<StagedFile>,
+    // `org_http_tools`: the organization's registered HTTP tools this
+    // request declared, from `resolve_org_http_tools`.
+    org_http_tools: Vec<HttpToolConfig>,
     response_owner: Option<ResponseOwner>,
     // `container_id_hint`: pre-resolved container id this response
     // should attach to (or create under). Derived from
@@ -492,6 +502,7 @@ pub fn apply_streaming_pipeline(
     request_id: Option<String>,
     response: Response<Body>,
     persistence: Option<PersistenceHandle>,
+    max_iterations: Option<usize>,
 ) -> Response<Body> {
     if !response.status().is_success() {
         return response;
@@ -630,6 +641,25 @@ pub fn apply_streaming_pipeline(
         }
     }
 
+    let http_tools = HttpToolExecutor::new(
+        state.http_client.clone(),
+        &state.config.features,
+        UrlValidationOptions {
+            allow_loopback: state.config.server.allow_loopback_urls,
+            allow_private: state.config.server.allow_private_urls,
+        },
+        payload,
+        org_http_tools,
+        state.db.clone().map(|db| HttpToolAudit {
+            db,
+            principal: principal.clone(),
+            response_id: persistence.as_ref().map(|h| h.response_id.clone()),
+        }),
+    );
+    if http_tools.has_tools() {
+        tools.push(Arc::new(http_tools));
+    }
+
     // MCP tool executor — engages when `[features.mcp].mode =
     // hadrian_hosted` is configured and the request carries any
     // `mcp` tool entries. Under `passthrough_openai` the upstream
@@ -680,6 +710,12 @@ pub fn apply_streaming_pipeline(
         let callback_provider_name = provider_name.clone();
         let callback_provider_config = provider_config.clone();
         let callback_model_name = model_name.clone();
+        let callback_tenant = Tenant {
+            org_id: principal.org_id,
+            project_id: principal.project_id,
+            api_key_id: principal.api_key_id,
+            ..Default::default()
+        };
         let provider_callback: ProviderCallback = Arc::new(move |payload| {
             let state = callback_state.clone();
             let provider_name = callback_provider_name.clone();
@@ -688,10 +724,20 @@ pub fn apply_streaming_pipeline(
             Box::pin(async move {
                 let mut payload = payload;
                 payload.model = Some(model_name);
-                ResponsesExecutor::execute(&state, &provider_name, &provider_config, payload).await
+                execute_provider::<ResponsesExecutor>(
+                    &state,
+                    &provider_name,
+                    &provider_config,
+                    payload,
+                    &callback_tenant,
+                )
+                .await
             })
         });
-        let max_iterations = state.config.features.server_tools.max_iterations;
+        let configured_max_iterations = state.config.features.server_tools.max_iterations;
+        let max_iterations = max_iterations.map_or(configured_max_iterations, |n| {
+            n.min(configured_max_iterations)
+        });
         // Every server tool synthesizes its own spec-shaped output items
         // (web_search_call / file_search_call / shell_call / mcp_call …)
         // and suppresses the rewritten function-call plumbing via
@@ -41,7 +41,7 @@ type HmacSha256 = Hmac<Sha256>;
 /// Format: `t=<unix-seconds>,v1=<hex-sha256>`. The signed payload is
 /// `"<unix>.<body>"` so a captured request can't be replayed against a
 /// receiver that enforces timestamp freshness.
-const SIGNATURE_HEADER: &str = "X-Hadrian-Signature";
+pub(crate) const SIGNATURE_HEADER: &str = "X-Hadrian-Signature";
 
 /// Entry-type marker used when pushing failed webhook deliveries to
 /// the DLQ. Surfaced in `/admin/v1/dlq` filters.
@@ -350,7 +350,7 @@ async fn deliver_with_retry(shared: &DispatcherInner, event: &WebhookEvent) -> b
 /// request from being replayed against a receiver that enforces a
 /// freshness window (the receiver re-signs with its own copy of
 /// `body` and the timestamp from the header, then compares).
-fn sign_payload(secret: &str, body: &[u8], now: DateTime<Utc>) -> String {
+pub(crate) fn sign_payload(secret: &str, body: &[u8], now: DateTime<Utc>) -> String {
     let ts = now.timestamp();
     let mut mac =
         HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC-SHA256 accepts any key length");
@@ -0,0 +1,1012 @@
+//! Scheduled report generation, rendering and delivery.
+//!
+//! Reports are defined in `[features.scheduled_reports]` and run per
+//! organization by the `scheduled_reports` background job. Each run:
+//!
+//! 1. Gathers data for one org over a closed reporting period
+//!    ([`ReportPeriod`]).
+//! 2. Renders it as JSON, CSV or HTML.
+//! 3. Delivers it by email (attachment) or webhook (POST body).
+//!
+//! The job records every attempt in `scheduled_report_runs`; this module
+//! is stateless apart from the HTTP client and SMTP transport.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::{collections::BTreeMap, fmt::Write as _, sync::Arc, time::Duration};
+
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, NaiveTime, Utc};
+use reqwest::Client;
+use serde::Serialize;
+use thiserror::Error;
+use uuid::Uuid;
+
+#[cfg(feature = "smtp")]
+use super::email::{EmailAttachment, EmailSender, OutgoingEmail};
+use super::responses_webhook::{SIGNATURE_HEADER, sign_payload};
+use crate::{
+    config::{ReportDelivery, ReportFormat, ReportKind, ReportSchedule, ScheduledReportConfig},
+    db::{DateRange, DbError, DbPool, ListParams},
+    models::{ApiKey, AuditLogQuery, Organization},
+};
+
+/// Page size used when walking API keys and audit logs.
+const PAGE_SIZE: i64 = 500;
+
+/// Keys expiring within this many days are flagged in key hygiene reports.
+const EXPIRING_SOON_DAYS: i64 = 14;
+
+/// Maximum number of individual guardrail events listed in a report.
+/// Totals always cover the whole period.
+const MAX_RECENT_GUARDRAIL_EVENTS: usize = 50;
+
+#[derive(Debug, Error)]
+pub enum ReportError {
+    #[error("Database error: {0}")]
+    Database(#[from] DbError),
+
+    #[error("Render error: {0}")]
+    Render(String),
+
+    #[error("Delivery failed: {0}")]
+    Delivery(String),
+}
+
+/// A closed reporting period. Both bounds are inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ReportPeriod {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl ReportPeriod {
+    /// The most recent complete period before `today` for `schedule`.
+    ///
+    /// Weekly periods run Monday to Sunday; monthly periods cover the
+    /// previous calendar month.
+    pub fn previous(schedule: ReportSchedule, today: NaiveDate) -> Self {
+        match schedule {
+            ReportSchedule::Daily => {
+                let day = today.pred_opt().expect("date in range");
+                Self {
+                    start: day,
+                    end: day,
+                }
+            }
+            ReportSchedule::Weekly => {
+                let this_monday =
+                    today - Days::new(u64::from(today.weekday().num_days_from_monday()));
+                Self {
+                    start: this_monday - Days::new(7),
+                    end: this_monday - Days::new(1),
+                }
+            }
+            ReportSchedule::Monthly => {
+                let first_of_month = today.with_day(1).expect("day 1 always valid");
+                Self {
+                    start: first_of_month - Months::new(1),
+                    end: first_of_month - Days::new(1),
+                }
+            }
+        }
+    }
+
+    fn start_time(&self) -> DateTime<Utc> {
+        self.start.and_time(NaiveTime::MIN).and_utc()
+    }
+
+    /// Exclusive upper bound as a timestamp (midnight after `end`).
+    fn end_time_exclusive(&self) -> DateTime<Utc> {
+        (self.end + Days::new(1)).and_time(NaiveTime::MIN).and_utc()
+    }
+}
+
+/// A generated report, before rendering.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportDocument {
+    pub report_name: String,
+    pub org_id: Uuid,
+    pub org_slug: String,
+    pub org_name: String,
+    pub period: ReportPeriod,
+    pub generated_at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub data: ReportData,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", content = "data", rename_all = "snake_case")]
+pub enum ReportData {
+    UsageSummary(UsageSummaryReport),
+    KeyHygiene(KeyHygieneReport),
+    GuardrailViolations(GuardrailViolationsReport),
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageSummaryReport {
+    /// Total cost in microcents (1/1,000,000 of a dollar)
+    pub total_cost_microcents: i64,
+    pub request_count: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub total_tokens: i64,
+    /// Per-model breakdown, most expensive first.
+    pub models: Vec<UsageModelRow>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageModelRow {
+    pub model: String,
+    pub total_cost_microcents: i64,
+    pub request_count: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct KeyHygieneReport {
+    pub stale_after_days: u32,
+    pub active_keys: usize,
+    pub never_used: usize,
+    pub stale: usize,
+    pub no_expiry: usize,
+    pub expiring_soon: usize,
+    /// Active keys with at least one finding.
+    pub findings: Vec<KeyFinding>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct KeyFinding {
+    pub key_id: Uuid,
+    pub name: String,
+    pub key_prefix: String,
+    /// `organization` or `project:<slug>`
+    pub owner: String,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+    /// One or more of `never_used`, `stale`, `no_expiry`, `expiring_soon`.
+    pub issues: Vec<&'static str>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GuardrailViolationsReport {
+    pub total_events: usize,
+    /// Event count per action (`block`, `warn`, `redact`, ...).
+    pub by_action: BTreeMap<String, usize>,
+    /// Violation count per category.
+    pub by_category: BTreeMap<String, usize>,
+    /// Most recent events, newest first.
+    pub recent: Vec<GuardrailEvent>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GuardrailEvent {
+    pub timestamp: DateTime<Utc>,
+    pub action: String,
+    pub stage: Option<String>,
+    pub categories: Vec<String>,
+}
+
+/// Generates, renders and delivers scheduled reports.
+#[derive(Clone)]
+pub struct ScheduledReportService {
+    db: Arc<DbPool>,
+    http: Client,
+    #[cfg(feature = "smtp")]
+    email: Option<EmailSender>,
+}
+
+impl ScheduledReportService {
+    pub fn new(db: Arc<DbPool>, http: Client) -> Self {
+        Self {
+            db,
+            http,
+            #[cfg(feature = "smtp")]
+            email: None,
+        }
+    }
+
+    /// Attach an SMTP sender for reports with `delivery.type = "email"`.
+    #[cfg(feature = "smtp")]
+    pub fn with_email(mut self, email: EmailSender) -> Self {
+        self.email = Some(email);
+        self
+    }
+
+    /// Gather the data for `report` for one organization.
+    pub async fn generate(
+        &self,
+        report: &ScheduledReportConfig,
+        org: &Organization,
+        period: ReportPeriod,
+        now: DateTime<Utc>,
+    ) -> Result<ReportDocument, ReportError> {
+        let data = match report.kind {
+            ReportKind::UsageSummary => {
+                ReportData::UsageSummary(self.usage_summary(org.id, period).await?)
+            }
+            ReportKind::KeyHygiene => {
+                ReportData::KeyHygiene(self.key_hygiene(org.id, report.stale_key_days, now).await?)
+            }
+            ReportKind::GuardrailViolations => {
+                ReportData::GuardrailViolations(self.guardrail_violations(org.id, period).await?)
+            }
+        };
+
+        Ok(ReportDocument {
+            report_name: report.name.clone(),
+            org_id: org.id,
+            org_slug: org.slug.clone(),
+            org_name: org.name.clone(),
+            period,
+            generated_at: now,
+            data,
+        })
+    }
+
+    async fn usage_summary(
+        &self,
+        org_id: Uuid,
+        period: ReportPeriod,
+    ) -> Result<UsageSummaryReport, ReportError> {
+        let range = DateRange {
+            start: period.start,
+            end: period.end,
+        };
+        let summary = self
+            .db
+            .usage()
+            .get_summary_by_org(org_id, range.clone())
+            .await?;
+        let mut models = self
+            .db
+            .usage()
+            .get_model_usage_by_org(org_id, range)
+            .await?;
+        models.sort_by(|a, b| b.total_cost_microcents.cmp(&a.total_cost_microcents));
+
+        Ok(UsageSummaryReport {
+            total_cost_microcents: summary.total_cost_microcents,
+            request_count: summary.request_count,
+            input_tokens: summary.input_tokens,
+            output_tokens: summary.output_tokens,
+            total_tokens: summary.total_tokens,
+            models: models
+                .into_iter()
+                .map(|m| UsageModelRow {
+                    model: m.model,
+                    total_cost_microcents: m.total_cost_microcents,
+                    request_count: m.request_count,
+                    input_tokens: m.input_tokens,
+                    output_tokens: m.output_tokens,
+                })
+                .collect(),
+        })
+    }
+
+    async fn key_hygiene(
+        &self,
+        org_id: Uuid,
+        stale_after_days: u32,
+        now: DateTime<Utc>,
+    ) -> Result<KeyHygieneReport, ReportError> {
+        let mut keys: Vec<(String, ApiKey)> = Vec::new();
+
+        let mut params = ListParams {
+            limit: Some(PAGE_SIZE),
+            ..Default::default()
+        };
+        loop {
+            let page = self
+                .db
+                .api_keys()
+                .list_by_org(org_id, params.clone())
+                .await?;
+            keys.extend(
+                page.items
+                    .into_iter()
+                    .map(|k| ("organization".to_string(), k)),
+            );
+            match page.cursors.next {
+                Some(next) if page.has_more => params.cursor = Some(next),
+                _ => break,
+            }
+        }
+
+        let mut project_params = ListParams {
+            limit: Some(PAGE_SIZE),
+            ..Default::default()
+        };
+        loop {
+            let projects = self
+                .db
+                .projects()
+                .list_by_org(org_id, project_params.clone())
+                .await?;
+            for project in &projects.items {
+                let mut params = ListParams {
+                    limit: Some(PAGE_SIZE),
+                    ..Default::default()
+                };
+                loop {
+                    let page = self
+                        .db
+                        .api_keys()
+                        .list_by_project(project.id, params.clone())
+                        .await?;
+                    keys.extend(
+                        page.items
+                            .into_iter()
+                            .map(|k| (format!("project:{}", project.slug), k)),
+                    );
+                    match page.cursors.next {
+                        Some(next) if page.has_more => params.cursor = Some(next),
+                        _ => break,
+                    }
+                }
+            }
+            match projects.cursors.next {
+                Some(next) if projects.has_more => project_params.cursor = Some(next),
+                _ => break,
+            }
+        }
+
+        Ok(build_key_hygiene(keys, stale_after_days, now))
+    }
+
+    async fn guardrail_violations(
+        &self,
+        org_id: Uuid,
+        period: ReportPeriod,
+    ) -> Result<GuardrailViolationsReport, ReportError> {
+        let mut report = GuardrailViolationsReport {
+            total_events: 0,
+            by_action: BTreeMap::new(),
+            by_category: BTreeMap::new(),
+            recent: Vec::new(),
+        };
+
+        let mut cursor = None;
+        loop {
+            let page = self
+                .db
+                .audit_logs()
+                .list(AuditLogQuery {
+                    resource_type: Some("guardrails".to_string()),
+                    org_id: Some(org_id),
+                    from: Some(period.start_time()),
+                    to: Some(period.end_time_exclusive()),
+                    limit: Some(PAGE_SIZE),
+                    cursor: cursor.take(),
+                    ..Default::default()
+                })
+                .await?;
+
+            for entry in &page.items {
+                let action = entry
+                    .action
+                    .strip_prefix("guardrails.")
+                    .unwrap_or(&entry.action)
+                    .to_string();
+                let categories: Vec<String> = entry
+                    .details
+                    .get("violations")
+                    .and_then(|v| v.as_array())
+                    .map(|violations| {
+                        violations
+                            .iter()
+                            .filter_map(|v| v.get("category").and_then(|c| c.as_str()))
+                            .map(String::from)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+                report.total_events += 1;
+                *report.by_action.entry(action.clone()).or_default() += 1;
+                for category in &categories {
+                    *report.by_category.entry(category.clone()).or_default() += 1;
+                }
+                // Audit logs are returned newest first
+                if report.recent.len() < MAX_RECENT_GUARDRAIL_EVENTS {
+                    report.recent.push(GuardrailEvent {
+                        timestamp: entry.timestamp,
+                        action,
+                        stage: entry
+                            .details
+                            .get("stage")
+                            .and_then(|s| s.as_str())
+                            .map(String::from),
+                        categories,
+                    });
+                }
+            }
+
+            match page.cursors.next {
+                Some(next) if page.has_more => cursor = Some(next.encode()),
+                _ => break,
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Deliver a rendered report through the report's configured channel.
+    pub async fn deliver(
+        &self,
+        report: &ScheduledReportConfig,
+        doc: &ReportDocument,
+        rendered: &str,
+    ) -> Result<(), ReportError> {
+        match &report.delivery {
+            ReportDelivery::Webhook {
+                url,
+                bearer_token,
+                signing_secret,
+                timeout_secs,
+            } => {
+                let body = rendered.as_bytes().to_vec();
+                let mut req = self
+                    .http
+                    .post(url)
+                    .timeout(Duration::from_secs(*timeout_secs))
+                    .header("Content-Type", report.format.content_type())
+                    .header("User-Agent", "hadrian-scheduled-reports/1")
+                    .header("X-Hadrian-Report-Name", &report.name)
+                    .header("X-Hadrian-Report-Kind", report.kind.as_str())
+                    .header("X-Hadrian-Report-Org", &doc.org_slug)
+                    .header(
+                        "X-Hadrian-Report-Period",
+                        format!("{}/{}", doc.period.start, doc.period.end),
+                    );
+                if let Some(secret) = signing_secret {
+                    req = req.header(SIGNATURE_HEADER, sign_payload(secret, &body, Utc::now()));
+                }
+                if let Some(token) = bearer_token {
+                    req = req.bearer_auth(token);
+                }
+
+                let resp = req
+                    .body(body)
+                    .send()
+                    .await
+                    .map_err(|e| ReportError::Delivery(e.to_string()))?;
+                if !resp.status().is_success() {
+                    return Err(ReportError::Delivery(format!(
+                        "webhook returned HTTP {}",
+                        resp.status()
+                    )));
+                }
+                Ok(())
+            }
+            #[cfg(feature = "smtp")]
+            ReportDelivery::Email { to, subject } => {
+                let Some(ref sender) = self.email else {
+                    return Err(ReportError::Delivery(
+                        "email delivery requires [notifications.email]".into(),
+                    ));
+                };
+                let subject = subject.clone().unwrap_or_else(|| {
+                    format!(
+                        "{} for {} ({} to {})",
+                        report.name, doc.org_name, doc.period.start, doc.period.end
+                    )
+                });
+                sender
+                    .send(OutgoingEmail {
+                        to: to.clone(),
+                        subject,
+                        text: format!(
+                            "The attached {} report covers {} from {} to {}.",
+                            report.kind.as_str(),
+                            doc.org_name,
+                            doc.period.start,
+                            doc.period.end
+                        ),
+                        attachments: vec![EmailAttachment {
+                            filename: report_filename(report, doc),
+                            content_type: report.format.content_type().to_string(),
+                            content: rendered.as_bytes().to_vec(),
+                        }],
+                    })
+                    .await
+                    .map_err(|e| ReportError::Delivery(e.to_string()))
+            }
+            #[cfg(not(feature = "smtp"))]
+            ReportDelivery::Email { .. } => Err(ReportError::Delivery(
+                "email delivery requires the 'smtp' feature".into(),
+            )),
+        }
+    }
+}
+
+/// Attachment filename, e.g. `weekly-usage-acme-2025-03-03.html`.
+pub fn report_filename(report: &ScheduledReportConfig, doc: &ReportDocument) -> String {
+    format!(
+        "{}-{}-{}.{}",
+        report.name,
+        doc.org_slug,
+        doc.period.start,
+        report.format.as_str()
+    )
+}
+
+fn build_key_hygiene(
+    keys: Vec<(String, ApiKey)>,
+    stale_after_days: u32,
+    now: DateTime<Utc>,
+) -> KeyHygieneReport {
+    let stale_cutoff = now - chrono::Duration::days(i64::from(stale_after_days));
+    let expiring_cutoff = now + chrono::Duration::days(EXPIRING_SOON_DAYS);
+
+    let mut report = KeyHygieneReport {
+        stale_after_days,
+        active_keys: 0,
+        never_used: 0,
+        stale: 0,
+        no_expiry: 0,
+        expiring_soon: 0,
+        findings: Vec::new(),
+    };
+
+    for (owner, key) in keys {
+        let active = key.revoked_at.is_none() && key.expires_at.is_none_or(|exp| exp > now);
+        if !active {
+            continue;
+        }
+        report.active_keys += 1;
+
+        let mut issues = Vec::new();
+        match key.last_used_at {
+            // Give freshly created keys the same grace period as used ones
+            None if key.created_at < stale_cutoff => {
+                issues.push("never_used");
+                report.never_used += 1;
+            }
+            Some(last_used) if last_used < stale_cutoff => {
+                issues.push("stale");
+                report.stale += 1;
+            }
+            _ => {}
+        }
+        match key.expires_at {
+            None => {
+                issues.push("no_expiry");
+                report.no_expiry += 1;
+            }
+            Some(exp) if exp <= expiring_cutoff => {
+                issues.push("expiring_soon");
+                report.expiring_soon += 1;
+            }
+            Some(_) => {}
+        }
+
+        if !issues.is_empty() {
+            report.findings.push(KeyFinding {
+                key_id: key.id,
+                name: key.name,
+                key_prefix: key.key_prefix,
+                owner,
+                created_at: key.created_at,
+                last_used_at: key.last_used_at,
+                expires_at: key.expires_at,
+                issues,
+            });
+        }
+    }
+
+    report
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Rendering
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Render a report document in the requested format.
+pub fn render(doc: &ReportDocument, format: ReportFormat) -> Result<String, ReportError> {
+    match format {
+        ReportFormat::Json => {
+            serde_json::to_string_pretty(doc).map_err(|e| ReportError::Render(e.to_string()))
+        }
+        ReportFormat::Csv => render_csv(doc),
+        ReportFormat::Html => Ok(render_html(doc)),
+    }
+}
+
+fn format_cost(microcents: i64) -> String {
+    format!("{:.2}", microcents as f64 / 1_000_000.0)
+}
+
+fn format_time(ts: Option<DateTime<Utc>>) -> String {
+    ts.map(|t| t.format("%Y-%m-%d %H:%M UTC").to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// One table per report kind, shared by the CSV and HTML renderers.
+fn tabulate(data: &ReportData) -> (Vec<&'static str>, Vec<Vec<String>>) {
+    match data {
+        ReportData::UsageSummary(usage) => (
+            vec![
+                "model",
+                "cost_usd",
+                "requests",
+                "input_tokens",
+                "output_tokens",
+            ],
+            usage
+                .models
+                .iter()
+                .map(|m| {
+                    vec![
+                        m.model.clone(),
+                        format_cost(m.total_cost_microcents),
+                        m.request_count.to_string(),
+                        m.input_tokens.to_string(),
+                        m.output_tokens.to_string(),
+                    ]
+                })
+                .collect(),
+        ),
+        ReportData::KeyHygiene(keys) => (
+            vec![
+                "key_prefix",
+                "name",
+                "owner",
+                "issues",
+                "created_at",
+                "last_used_at",
+                "expires_at",
+            ],
+            keys.findings
+                .iter()
+                .map(|f| {
+                    vec![
+                        f.key_prefix.clone(),
+                        f.name.clone(),
+                        f.owner.clone(),
+                        f.issues.join(" "),
+                        format_time(Some(f.created_at)),
+                        format_time(f.last_used_at),
+                        format_time(f.expires_at),
+                    ]
+                })
+                .collect(),
+        ),
+        ReportData::GuardrailViolations(guardrails) => (
+            vec!["timestamp", "action", "stage", "categories"],
+            guardrails
+                .recent
+                .iter()
+                .map(|e| {
+                    vec![
+                        format_time(Some(e.timestamp)),
+                        e.action.clone(),
+                        e.stage.clone().unwrap_or_default(),
+                        e.categories.join(" "),
+                    ]
+                })
+                .collect(),
+        ),
+    }
+}
+
+/// Summary lines shown above the table.
+fn summary_lines(data: &ReportData) -> Vec<(String, String)> {
+    match data {
+        ReportData::UsageSummary(usage) => vec![
+            (
+                "Total cost (USD)".into(),
+                format_cost(usage.total_cost_microcents),
+            ),
+            ("Requests".into(), usage.request_count.to_string()),
+            ("Input tokens".into(), usage.input_tokens.to_string()),
+            ("Output tokens".into(), usage.output_tokens.to_string()),
+        ],
+        ReportData::KeyHygiene(keys) => vec![
+            ("Active keys".into(), keys.active_keys.to_string()),
+            ("Never used".into(), keys.never_used.to_string()),
+            (
+                format!("Unused for {}+ days", keys.stale_after_days),
+                keys.stale.to_string(),
+            ),
+            ("No expiry".into(), keys.no_expiry.to_string()),
+            (
+                format!("Expiring within {EXPIRING_SOON_DAYS} days"),
+                keys.expiring_soon.to_string(),
+            ),
+        ],
+        ReportData::GuardrailViolations(guardrails) => {
+            let mut lines = vec![("Total events".into(), guardrails.total_events.to_string())];
+            lines.extend(
+                guardrails
+                    .by_action
+                    .iter()
+                    .map(|(action, count)| (format!("Action: {action}"), count.to_string())),
+            );
+            lines.extend(
+                guardrails
+                    .by_category
+                    .iter()
+                    .map(|(category, count)| (format!("Category: {category}"), count.to_string())),
+            );
+            lines
+        }
+    }
+}
+
+/// Prefix cells that a spreadsheet would evaluate as a formula.
+#[cfg(feature = "csv-export")]
+fn sanitize_csv_cell(value: String) -> String {
+    match value.chars().next() {
+        Some('=' | '+' | '-' | '@' | '\t' | '\r') => format!("'{value}"),
+        _ => value,
+    }
+}
+
+#[cfg(feature = "csv-export")]
+fn render_csv(doc: &ReportDocument) -> Result<String, ReportError> {
+    let (headers, rows) = tabulate(&doc.data);
+    let mut wtr = csv::Writer::from_writer(Vec::new());
+    wtr.write_record(&headers)
+        .map_err(|e| ReportError::Render(e.to_string()))?;
+    for row in rows {
+        wtr.write_record(row.into_iter().map(sanitize_csv_cell))
+            .map_err(|e| ReportError::Render(e.to_string()))?;
+    }
+    let bytes = wtr
+        .into_inner()
+        .map_err(|e| ReportError::Render(e.to_string()))?;
+    String::from_utf8(bytes).map_err(|e| ReportError::Render(e.to_string()))
+}
+
+#[cfg(not(feature = "csv-export"))]
+fn render_csv(_doc: &ReportDocument) -> Result<String, ReportError> {
+    Err(ReportError::Render(
+        "CSV reports require the 'csv-export' feature".into(),
+    ))
+}
+
+fn escape_html(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn render_html(doc: &ReportDocument) -> String {
+    let title = format!(
+        "{} — {} ({} to {})",
+        doc.report_name, doc.org_name, doc.period.start, doc.period.end
+    );
+    let (headers, rows) = tabulate(&doc.data);
+
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{}</title>\
+         <style>body{{font-family:sans-serif;margin:2em}}table{{border-collapse:collapse}}\
+         th,td{{border:1px solid #ccc;padding:4px 8px;text-align:left}}</style></head><body>\n",
+        escape_html(&title)
+    );
+    let _ = writeln!(html, "<h1>{}</h1>", escape_html(&title));
+
+    html.push_str("<table>\n");
+    for (label, value) in summary_lines(&doc.data) {
+        let _ = writeln!(
+            html,
+            "<tr><th>{}</th><td>{}</td></tr>",
+            escape_html(&label),
+            escape_html(&value)
+        );
+    }
+    html.push_str("</table>\n");
+
+    if rows.is_empty() {
+        html.push_str("<p>No entries for this period.</p>\n");
+    } else {
+        html.push_str("<h2>Details</h2>\n<table>\n<tr>");
+        for header in &headers {
+            let _ = write!(html, "<th>{}</th>", escape_html(header));
+        }
+        html.push_str("</tr>\n");
+        for row in &rows {
+            html.push_str("<tr>");
+            for cell in row {
+                let _ = write!(html, "<td>{}</td>", escape_html(cell));
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</table>\n");
+    }
+
+    let _ = writeln!(
+        html,
+        "<p><small>Generated {} by Hadrian Gateway</small></p>\n</body></html>",
+        doc.generated_at.format("%Y-%m-%d %H:%M UTC")
+    );
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ApiKeyOwner;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_previous_period_weekly_runs_monday_to_sunday() {
+        // Wednesday 2025-03-12 → previous week is Mon 3rd .. Sun 9th
+        let period = ReportPeriod::previous(ReportSchedule::Weekly, date(2025, 3, 12));
+        assert_eq!(period.start, date(2025, 3, 3));
+        assert_eq!(period.end, date(2025, 3, 9));
+
+        // On a Monday the just-finished week is reported
+        let period = ReportPeriod::previous(ReportSchedule::Weekly, date(2025, 3, 10));
+        assert_eq!(period.start, date(2025, 3, 3));
+        assert_eq!(period.end, date(2025, 3, 9));
+    }
+
+    #[test]
+    fn test_previous_period_monthly_and_daily() {
+        let period = ReportPeriod::previous(ReportSchedule::Monthly, date(2025, 3, 1));
+        assert_eq!(period.start, date(2025, 2, 1));
+        assert_eq!(period.end, date(2025, 2, 28));
+
+        let period = ReportPeriod::previous(ReportSchedule::Monthly, date(2025, 1, 15));
+        assert_eq!(period.start, date(2024, 12, 1));
+        assert_eq!(period.end, date(2024, 12, 31));
+
+        let period = ReportPeriod::previous(ReportSchedule::Daily, date(2025, 3, 1));
+        assert_eq!(period.start, date(2025, 2, 28));
+        assert_eq!(period.end, date(2025, 2, 28));
+    }
+
+    fn key(
+        name: &str,
+        created_days_ago: i64,
+        last_used_days_ago: Option<i64>,
+        expires_in_days: Option<i64>,
+        now: DateTime<Utc>,
+    ) -> ApiKey {
+        ApiKey {
+            id: Uuid::new_v4(),
+            key_prefix: format!("gw_{name}"),
+            name: name.to_string(),
+            owner: ApiKeyOwner::Organization {
+                org_id: Uuid::nil(),
+            },
+            budget_limit_cents: None,
+            budget_period: None,
+            created_at: now - chrono::Duration::days(created_days_ago),
+            expires_at: expires_in_days.map(|d| now + chrono::Duration::days(d)),
+            revoked_at: None,
+            last_used_at: last_used_days_ago.map(|d| now - chrono::Duration::days(d)),
+            scopes: None,
+            allowed_models: None,
+            ip_allowlist: None,
+            rate_limit_rpm: None,
+            rate_limit_tpm: None,
+            rotated_from_key_id: None,
+            rotation_grace_until: None,
+            sovereignty_requirements: None,
+            labels: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_key_hygiene_flags() {
+        let now = Utc::now();
+        let mut revoked = key("revoked", 400, None, None, now);
+        revoked.revoked_at = Some(now);
+        let keys = vec![
+            (
+                "organization".to_string(),
+                key("healthy", 10, Some(1), Some(60), now),
+            ),
+            (
+                "organization".to_string(),
+                key("new", 2, None, Some(60), now),
+            ),
+            (
+                "organization".to_string(),
+                key("unused", 120, None, Some(60), now),
+            ),
+            (
+                "project:web".to_string(),
+                key("stale", 200, Some(100), None, now),
+            ),
+            (
+                "organization".to_string(),
+                key("expiring", 30, Some(1), Some(3), now),
+            ),
+            (
+                "organization".to_string(),
+                key("expired", 30, Some(1), Some(-1), now),
+            ),
+            ("organization".to_string(), revoked),
+        ];
+
+        let report = build_key_hygiene(keys, 90, now);
+        assert_eq!(report.active_keys, 5);
+        assert_eq!(report.never_used, 1);
+        assert_eq!(report.stale, 1);
+        assert_eq!(report.no_expiry, 1);
+        assert_eq!(report.expiring_soon, 1);
+
+        let names: Vec<_> = report.findings.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["unused", "stale", "expiring"]);
+        let stale = &report.findings[1];
+        assert_eq!(stale.owner, "project:web");
+        assert_eq!(stale.issues, vec!["stale", "no_expiry"]);
+    }
+
+    fn usage_doc(model: &str) -> ReportDocument {
+        ReportDocument {
+            report_name: "weekly".into(),
+            org_id: Uuid::nil(),
+            org_slug: "acme".into(),
+            org_name: "Acme <Corp>".into(),
+            period: ReportPeriod {
+                start: date(2025, 3, 3),
+                end: date(2025, 3, 9),
+            },
+            generated_at: Utc::now(),
+            data: ReportData::UsageSummary(UsageSummaryReport {
+                total_cost_microcents: 1_500_000,
+                request_count: 3,
+                input_tokens: 100,
+                output_tokens: 50,
+                total_tokens: 150,
+                models: vec![UsageModelRow {
+                    model: model.into(),
+                    total_cost_microcents: 1_500_000,
+                    request_count: 3,
+                    input_tokens: 100,
+                    output_tokens: 50,
+                }],
+            }),
+        }
+    }
+
+    #[test]
+    fn test_render_json_includes_kind_and_period() {
+        let json = render(&usage_doc("gpt-4o"), ReportFormat::Json).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["kind"], "usage_summary");
+        assert_eq!(value["period"]["start"], "2025-03-03");
+        assert_eq!(value["data"]["total_cost_microcents"], 1_500_000);
+        assert_eq!(value["data"]["models"][0]["model"], "gpt-4o");
+    }
+
+    #[test]
+    fn test_render_html_escapes_values() {
+        let html = render(&usage_doc("<script>alert(1)</script>"), ReportFormat::Html).unwrap();
+        assert!(html.contains("Acme &lt;Corp&gt;"));
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("<td>1.50</td>"));
+    }
+
+    #[cfg(feature = "csv-export")]
+    #[test]
+    fn test_render_csv_defangs_formulas() {
+        let csv = render(&usage_doc("=HYPERLINK(\"x\")"), ReportFormat::Csv).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("model,cost_usd,requests,input_tokens,output_tokens")
+        );
+        assert!(lines.next().unwrap().starts_with("\"'=HYPERLINK"));
+    }
+}
@@ -0,0 +1,722 @@
+//! Recurring prompts run on a cron schedule.
+//!
+//! Organizations define scheduled tasks through the admin API; the
+//! `scheduled_tasks` job runs the ones that are due. A run sends the task's
+//! prompt to its model through the same routing and fallback chain a client
+//! request takes, logs the tokens used against the organization with the run
+//! ID as request ID, and delivers the answer: appended to a conversation, or
+//! POSTed to a webhook signed like the other outbound webhooks.
+//!
+//! Schedules are standard five-field cron expressions evaluated in UTC.
+
+use std::{str::FromStr, time::Duration};
+
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
+use serde_json::{Value, json};
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::responses_webhook::{SIGNATURE_HEADER, sign_payload};
+use crate::{
+    AppState,
+    api_types::{
+        CreateChatCompletionPayload,
+        chat_completion::{Message, MessageContent},
+    },
+    models::{
+        self, AppendMessages, CreateScheduledTaskRun, ScheduledTask, ScheduledTaskDelivery,
+        ScheduledTaskRunStatus, UsageLogEntry,
+    },
+    pricing::CostPricingSource,
+    providers::Tenant,
+    routes::execution::{ChatCompletionExecutor, execute_with_fallback},
+    routing::{resolver, route_models_extended},
+    validation::{UrlValidationOptions, pinned_reqwest_client, validate_base_url_opts},
+};
+
+/// Max response body read from the provider.
+const MAX_RESPONSE_BYTES: usize = 4 * 1024 * 1024;
+
+/// How far ahead [`CronSchedule::next_after`] looks. Long enough for
+/// schedules that only match on leap days.
+const MAX_LOOKAHEAD_DAYS: i64 = 5 * 366;
+
+/// Upcoming runs checked against `min_interval_secs` when a schedule is saved.
+const INTERVAL_CHECK_RUNS: usize = 100;
+
+#[derive(Debug, Error)]
+pub enum ScheduledTaskError {
+    #[error("Model routing failed: {0}")]
+    Routing(String),
+
+    #[error("Provider error: {0}")]
+    Provider(String),
+
+    #[error("Failed to parse model response: {0}")]
+    Parse(String),
+
+    #[error("Delivery failed: {0}")]
+    Delivery(String),
+
+    #[error("Run timed out")]
+    Timeout,
+}
+
+// ============================================================================
+// Cron schedules
+// ============================================================================
+
+/// A parsed five-field cron expression (`minute hour day-of-month month
+/// day-of-week`), evaluated in UTC.
+///
+/// Fields accept `*`, values, ranges (`1-5`), steps (`*/15`, `0-30/10`) and
+/// comma-separated lists of those. Day-of-week runs from 0 (Sunday) to 6, with
+/// 7 also meaning Sunday. `@hourly`, `@daily`, `@weekly` and `@monthly` are
+/// accepted as shorthands. As in standard cron, when both day-of-month and
+/// day-of-week are restricted a day matches if either does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    days_restricted: bool,
+    weekdays_restricted: bool,
+}
+
+impl FromStr for CronSchedule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let expr = match s.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            other => other,
+        };
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(format!(
+                "expected 5 fields (minute hour day-of-month month day-of-week), got {}",
+                fields.len()
+            ));
+        };
+
+        let mut weekdays = parse_field(weekday, 0, 7, "day-of-week")?;
+        // 7 is Sunday too
+        if weekdays & (1 << 7) != 0 {
+            weekdays = (weekdays | 1) & !(1 << 7);
+        }
+
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59, "minute")?,
+            hours: parse_field(hour, 0, 23, "hour")?,
+            days: parse_field(day, 1, 31, "day-of-month")?,
+            months: parse_field(month, 1, 12, "month")?,
+            weekdays,
+            days_restricted: !day.starts_with('*'),
+            weekdays_restricted: !weekday.starts_with('*'),
+        })
+    }
+}
+
+/// Parse one cron field into a bit set of the values it matches.
+fn parse_field(field: &str, min: u32, max: u32, name: &str) -> Result<u64, String> {
+    let number = |s: &str| {
+        s.parse::<u32>()
+            .map_err(|_| format!("invalid {name} value '{s}'"))
+    };
+
+    let mut bits = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => match number(step)? {
+                0 => return Err(format!("{name} step must be greater than 0")),
+                step => (range, step),
+            },
+            None => (part, 1),
+        };
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (number(start)?, number(end)?)
+        } else {
+            let value = number(range)?;
+            // `5/15` means every 15 starting at 5
+            (value, if step > 1 { max } else { value })
+        };
+        if start < min || end > max || start > end {
+            return Err(format!("{name} range '{range}' must be within {min}-{max}"));
+        }
+        for value in (start..=end).step_by(step as usize) {
+            bits |= 1 << value;
+        }
+    }
+    Ok(bits)
+}
+
+impl CronSchedule {
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let day = self.days & (1 << date.day()) != 0;
+        let weekday = self.weekdays & (1 << date.weekday().num_days_from_sunday()) != 0;
+        match (self.days_restricted, self.weekdays_restricted) {
+            (true, true) => day || weekday,
+            (true, false) => day,
+            (false, true) => weekday,
+            (false, false) => true,
+        }
+    }
+
+    /// The first time the schedule matches strictly after `after`, or `None`
+    /// if it never matches (e.g. `0 0 31 2 *`).
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let start =
+            after.naive_utc().with_second(0)?.with_nanosecond(0)? + chrono::Duration::minutes(1);
+        let limit = start + chrono::Duration::days(MAX_LOOKAHEAD_DAYS);
+        let midnight = |date: NaiveDate| date.and_hms_opt(0, 0, 0);
+
+        let mut t: NaiveDateTime = start;
+        while t < limit {
+            if self.months & (1 << t.month()) == 0 {
+                let (year, month) = if t.month() == 12 {
+                    (t.year() + 1, 1)
+                } else {
+                    (t.year(), t.month() + 1)
+                };
+                t = midnight(NaiveDate::from_ymd_opt(year, month, 1)?)?;
+            } else if !self.matches_day(t.date()) {
+                t = midnight(t.date().succ_opt()?)?;
+            } else if self.hours & (1 << t.hour()) == 0 {
+                t = t.with_minute(0)? + chrono::Duration::hours(1);
+            } else if self.minutes & (1 << t.minute()) == 0 {
+                t += chrono::Duration::minutes(1);
+            } else {
+                return Some(Utc.from_utc_datetime(&t));
+            }
+        }
+        None
+    }
+}
+
+/// Parse a task's schedule and check it doesn't run more often than every
+/// `min_interval_secs`. Returns the first run after `now`.
+pub fn validate_schedule(
+    schedule: &str,
+    min_interval_secs: u64,
+    now: DateTime<Utc>,
+) -> Result<DateTime<Utc>, String> {
+    let cron: CronSchedule = schedule.parse()?;
+    let first = cron
+        .next_after(now)
+        .ok_or_else(|| "schedule never runs".to_string())?;
+
+    let min_interval = chrono::Duration::seconds(min_interval_secs as i64);
+    let mut previous = first;
+    for _ in 0..INTERVAL_CHECK_RUNS {
+        let Some(next) = cron.next_after(previous) else {
+            break;
+        };
+        if next - previous < min_interval {
+            return Err(format!(
+                "schedule runs more often than the minimum interval of {min_interval_secs} seconds"
+            ));
+        }
+        previous = next;
+    }
+    Ok(first)
+}
+
+/// When a task next runs after `now`. `None` if its schedule no longer parses
+/// or never matches, which leaves the task unscheduled.
+pub fn next_run(task: &ScheduledTask, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    task.schedule
+        .parse::<CronSchedule>()
+        .ok()
+        .and_then(|cron| cron.next_after(now))
+}
+
+// ============================================================================
+// Runs
+// ============================================================================
+
+/// What the model answered with.
+#[derive(Debug, Default, PartialEq)]
+struct Completion {
+    text: String,
+    input_tokens: i64,
+    output_tokens: i64,
+}
+
+/// Run a task once: generate its answer and deliver it. Never fails; the
+/// returned run records what went wrong.
+pub async fn run_task(state: &AppState, task: &ScheduledTask) -> CreateScheduledTaskRun {
+    let config = &state.config.features.scheduled_tasks;
+    let started_at = Utc::now();
+    let mut run = CreateScheduledTaskRun {
+        id: Uuid::new_v4(),
+        task_id: task.id,
+        status: ScheduledTaskRunStatus::Failed,
+        output_text: None,
+        error: None,
+        input_tokens: 0,
+        output_tokens: 0,
+        cost_microcents: None,
+        started_at,
+        finished_at: started_at,
+    };
+
+    let work = async {
+        generate(state, task, &mut run).await?;
+        let output = run.output_text.clone().unwrap_or_default();
+        deliver(state, task, run.id, &output).await
+    };
+    let result = tokio::time::timeout(Duration::from_secs(config.timeout_secs), work).await;
+
+    match result {
+        Ok(Ok(())) => run.status = ScheduledTaskRunStatus::Succeeded,
+        Ok(Err(e)) => run.error = Some(e.to_string()),
+        Err(_) => run.error = Some(ScheduledTaskError::Timeout.to_string()),
+    }
+    run.finished_at = Utc::now();
+    run
+}
+
+/// Send the task's prompt to its model, filling in the run's output, tokens
+/// and cost, and log the usage against the task's organization.
+async fn generate(
+    state: &AppState,
+    task: &ScheduledTask,
+    run: &mut CreateScheduledTaskRun,
+) -> Result<(), ScheduledTaskError> {
+    let routed = route_models_extended(Some(task.model.as_str()), None, &state.config.providers)
+        .map_err(|e| ScheduledTaskError::Routing(e.to_string()))?;
+    let resolved = resolver::resolve_to_provider(
+        routed,
+        state.db.as_ref(),
+        state.cache.as_ref(),
+        state.secrets.as_ref(),
+        &state.provider_settings,
+        None,
+    )
+    .await
+    .map_err(|e| ScheduledTaskError::Routing(e.to_string()))?;
+
+    let payload = build_payload(
+        task,
+        &resolved.model,
+        state.config.features.scheduled_tasks.max_output_tokens,
+    );
+    let tenant = Tenant {
+        org_id: Some(task.org_id),
+        ..Default::default()
+    };
+    let result = execute_with_fallback::<ChatCompletionExecutor>(
+        state,
+        resolved.provider_name,
+        resolved.provider_config,
+        resolved.model,
+        payload,
+        None,
+        &tenant,
+    )
+    .await
+    .map_err(|e| ScheduledTaskError::Provider(e.to_string()))?;
+
+    let status = result.response.status();
+    let body = axum::body::to_bytes(result.response.into_body(), MAX_RESPONSE_BYTES)
+        .await
+        .map_err(|e| ScheduledTaskError::Provider(format!("Failed to read response: {e}")))?;
+    if !status.is_success() {
+        let message = serde_json::from_slice::<Value>(&body)
+            .ok()
+            .and_then(|v| {
+                v.pointer("/error/message")
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+            })
+            .unwrap_or_else(|| format!("HTTP {status}"));
+        return Err(ScheduledTaskError::Provider(message));
+    }
+    let completion = parse_completion(&body)?;
+
+    let cost = state.pricing.calculate_cost(
+        &result.provider_name,
+        &result.model_name,
+        completion.input_tokens,
+        completion.output_tokens,
+    );
+    run.output_text = Some(completion.text);
+    run.input_tokens = completion.input_tokens;
+    run.output_tokens = completion.output_tokens;
+    run.cost_microcents = cost.map(|(microcents, _)| microcents);
+
+    if let Some(db) = &state.db {
+        let mut entry = usage_entry(task, run, &result.provider_name, &result.model_name);
+        entry.pricing_source = cost.map_or(CostPricingSource::None, |(_, source)| source);
+        if let Err(e) = db.usage().log(entry).await {
+            tracing::warn!(task_id = %task.id, run_id = %run.id, error = %e, "Failed to log scheduled task usage");
+        }
+    }
+    Ok(())
+}
+
+fn build_payload(
+    task: &ScheduledTask,
+    model: &str,
+    max_output_tokens: u64,
+) -> CreateChatCompletionPayload {
+    let mut messages = Vec::new();
+    if let Some(instructions) = &task.instructions {
+        messages.push(Message::System {
+            content: MessageContent::Text(instructions.clone()),
+            name: None,
+        });
+    }
+    messages.push(Message::User {
+        content: MessageContent::Text(task.prompt.clone()),
+        name: None,
+    });
+
+    CreateChatCompletionPayload {
+        messages,
+        model: Some(model.to_string()),
+        stream: false,
+        temperature: None,
+        response_format: None,
+        models: None,
+        frequency_penalty: None,
+        logit_bias: None,
+        logprobs: None,
+        top_logprobs: None,
+        max_completion_tokens: Some(max_output_tokens),
+        max_tokens: None,
+        metadata: None,
+        presence_penalty: None,
+        reasoning: None,
+        seed: None,
+        stop: None,
+        stream_options: None,
+        tool_choice: None,
+        tools: None,
+        top_p: None,
+        user: None,
+        sovereignty_requirements: None,
+    }
+}
+
+fn parse_completion(body: &[u8]) -> Result<Completion, ScheduledTaskError> {
+    let response: Value = serde_json::from_slice(body)
+        .map_err(|e| ScheduledTaskError::Parse(format!("Invalid JSON response: {e}")))?;
+    let text = response
+        .pointer("/choices/0/message/content")
+        .and_then(Value::as_str)
+        .filter(|text| !text.trim().is_empty())
+        .ok_or_else(|| ScheduledTaskError::Parse("Missing content in response".to_string()))?;
+    let tokens = |pointer: &str| {
+        response
+            .pointer(pointer)
+            .and_then(Value::as_i64)
+            .unwrap_or(0)
+    };
+
+    Ok(Completion {
+        text: text.to_string(),
+        input_tokens: tokens("/usage/prompt_tokens"),
+        output_tokens: tokens("/usage/completion_tokens"),
+    })
+}
+
+/// Usage record for a run's tokens. The run ID is the request ID so the
+/// run's usage can be found.
+fn usage_entry(
+    task: &ScheduledTask,
+    run: &CreateScheduledTaskRun,
+    provider: &str,
+    model: &str,
+) -> UsageLogEntry {
+    UsageLogEntry {
+        request_id: run.id.to_string(),
+        api_key_id: None,
+        user_id: None,
+        org_id: Some(task.org_id),
+        project_id: None,
+        team_id: None,
+        service_account_id: None,
+        model: model.to_string(),
+        provider: provider.to_string(),
+        input_tokens: run.input_tokens,
+        output_tokens: run.output_tokens,
+        cost_microcents: run.cost_microcents,
+        http_referer: None,
+        request_at: run.started_at,
+        streamed: false,
+        cached_tokens: 0,
+        reasoning_tokens: 0,
+        finish_reason: None,
+        latency_ms: None,
+        cancelled: false,
+        status_code: None,
+        pricing_source: CostPricingSource::None,
+        image_count: None,
+        audio_seconds: None,
+        character_count: None,
+        provider_source: None,
+        record_type: "model".to_string(),
+        tool_name: None,
+        tool_query: None,
+        tool_url: None,
+        tool_bytes_fetched: None,
+        tool_results_count: None,
+        tool_runtime_seconds: None,
+        tool_exit_code: None,
+        jwt_subject: None,
+        error_code: None,
+        structured_output_repairs: None,
+        context_original_tokens: None,
+        context_compressed_tokens: None,
+        degraded_from_model: None,
+        degradation_reason: None,
+        ttft_ms: None,
+        inter_token_latency_ms: None,
+        output_tokens_per_second: None,
+        idempotency_key: None,
+        provider_attempts: None,
+        suspected_duplicate_attempts: None,
+        tags: None,
+    }
+}
+
+/// Deliver a run's output to the task's conversation or webhook.
+async fn deliver(
+    state: &AppState,
+    task: &ScheduledTask,
+    run_id: Uuid,
+    output: &str,
+) -> Result<(), ScheduledTaskError> {
+    match &task.delivery {
+        ScheduledTaskDelivery::Conversation { conversation_id } => {
+            let Some(services) = &state.services else {
+                return Err(ScheduledTaskError::Delivery(
+                    "conversation delivery requires a database".to_string(),
+                ));
+            };
+            // The conversation may have been deleted or moved since the task
+            // was saved
+            services
+                .conversations
+                .get_by_id_and_org(*conversation_id, task.org_id)
+                .await
+                .map_err(|e| ScheduledTaskError::Delivery(e.to_string()))?
+                .ok_or_else(|| {
+                    ScheduledTaskError::Delivery("conversation no longer exists".to_string())
+                })?;
+            let message = |role: &str, content: &str| models::Message {
+                role: role.to_string(),
+                content: content.to_string(),
+                annotations: vec![],
+                attachments: vec![],
+            };
+            services
+                .conversations
+                .append_messages(
+                    *conversation_id,
+                    AppendMessages {
+                        messages: vec![message("user", &task.prompt), message("assistant", output)],
+                    },
+                )
+                .await
+                .map_err(|e| ScheduledTaskError::Delivery(e.to_string()))?;
+            Ok(())
+        }
+        ScheduledTaskDelivery::Webhook {
+            url,
+            signing_secret,
+        } => {
+            // Checked when the task was saved, and again here since DNS may
+            // have changed since
+            let validated = validate_base_url_opts(
+                url,
+                UrlValidationOptions {
+                    allow_loopback: state.config.server.allow_loopback_urls,
+                    allow_private: state.config.server.allow_private_urls,
+                },
+            )
+            .map_err(|e| ScheduledTaskError::Delivery(format!("blocked webhook URL: {e}")))?;
+            let client = pinned_reqwest_client(&validated)
+                .map_err(|e| ScheduledTaskError::Delivery(e.to_string()))?;
+
+            let body = serde_json::to_vec(&webhook_body(task, run_id, output))
+                .map_err(|e| ScheduledTaskError::Delivery(e.to_string()))?;
+            let mut req = client
+                .post(url)
+                .header("Content-Type", "application/json")
+                .header("User-Agent", "hadrian-scheduled-tasks/1");
+            if let Some(secret) = signing_secret {
+                req = req.header(SIGNATURE_HEADER, sign_payload(secret, &body, Utc::now()));
+            }
+
+            let resp = req
+                .body(body)
+                .send()
+                .await
+                .map_err(|e| ScheduledTaskError::Delivery(e.to_string()))?;
+            if !resp.status().is_success() {
+                return Err(ScheduledTaskError::Delivery(format!(
+                    "webhook returned HTTP {}",
+                    resp.status()
+                )));
+            }
+            Ok(())
+        }
+    }
+}
+
+fn webhook_body(task: &ScheduledTask, run_id: Uuid, output: &str) -> Value {
+    json!({
+        "type": "scheduled_task.run.succeeded",
+        "task": {
+            "id": task.id,
+            "org_id": task.org_id,
+            "name": task.name,
+            "schedule": task.schedule,
+            "model": task.model,
+        },
+        "run_id": run_id,
+        "output_text": output,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn next(schedule: &str, after: &str) -> Option<DateTime<Utc>> {
+        schedule
+            .parse::<CronSchedule>()
+            .unwrap()
+            .next_after(at(after))
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_expressions() {
+        for expr in [
+            "",
+            "* * * *",
+            "* * * * * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "* * 0 * *",
+            "* * * 13 *",
+            "* * * * 8",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "a * * * *",
+            "1,,2 * * * *",
+        ] {
+            assert!(
+                expr.parse::<CronSchedule>().is_err(),
+                "{expr:?} should fail"
+            );
+        }
+    }
+
+    #[test]
+    fn test_next_after_simple_schedules() {
+        // Strictly after, even on an exact match
+        assert_eq!(
+            next("0 8 * * *", "2025-03-10T08:00:00Z"),
+            Some(at("2025-03-11T08:00:00Z"))
+        );
+        assert_eq!(
+            next("0 8 * * *", "2025-03-10T07:59:30Z"),
+            Some(at("2025-03-10T08:00:00Z"))
+        );
+        assert_eq!(
+            next("*/15 * * * *", "2025-03-10T08:07:00Z"),
+            Some(at("2025-03-10T08:15:00Z"))
+        );
+        assert_eq!(
+            next("@hourly", "2025-12-31T23:30:00Z"),
+            Some(at("2026-01-01T00:00:00Z"))
+        );
+        assert_eq!(
+            next("@monthly", "2025-01-15T00:00:00Z"),
+            Some(at("2025-02-01T00:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn test_next_after_weekdays() {
+        // 2025-03-08 is a Saturday
+        assert_eq!(
+            next("30 9 * * 1-5", "2025-03-08T12:00:00Z"),
+            Some(at("2025-03-10T09:30:00Z"))
+        );
+        // 7 is Sunday
+        assert_eq!(
+            next("0 0 * * 7", "2025-03-08T12:00:00Z"),
+            Some(at("2025-03-09T00:00:00Z"))
+        );
+        // Day-of-month or day-of-week when both are restricted
+        assert_eq!(
+            next("0 0 15 * 1", "2025-03-08T12:00:00Z"),
+            Some(at("2025-03-10T00:00:00Z"))
+        );
+        assert_eq!(
+            next("0 0 15 * 1", "2025-03-12T12:00:00Z"),
+            Some(at("2025-03-15T00:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn test_next_after_rare_and_impossible_dates() {
+        assert_eq!(
+            next("0 0 29 2 *", "2025-03-01T00:00:00Z"),
+            Some(at("2028-02-29T00:00:00Z"))
+        );
+        assert_eq!(next("0 0 31 2 *", "2025-03-01T00:00:00Z"), None);
+    }
+
+    #[test]
+    fn test_validate_schedule_enforces_min_interval() {
+        let now = at("2025-03-10T07:00:00Z");
+        assert_eq!(
+            validate_schedule("0 8 * * *", 3600, now),
+            Ok(at("2025-03-10T08:00:00Z"))
+        );
+        assert!(validate_schedule("*/30 * * * *", 3600, now).is_err());
+        // Hourly is fine, but a second run within the hour is not
+        assert!(validate_schedule("0 * * * *", 3600, now).is_ok());
+        assert!(validate_schedule("0,30 8 * * *", 3600, now).is_err());
+        assert!(validate_schedule("0 0 31 2 *", 3600, now).is_err());
+    }
+
+    #[test]
+    fn test_parse_completion() {
+        let body = serde_json::to_vec(&json!({
+            "choices": [{ "message": { "role": "assistant", "content": "All quiet." } }],
+            "usage": { "prompt_tokens": 12, "completion_tokens": 3 }
+        }))
+        .unwrap();
+        assert_eq!(
+            parse_completion(&body).unwrap(),
+            Completion {
+                text: "All quiet.".to_string(),
+                input_tokens: 12,
+                output_tokens: 3,
+            }
+        );
+
+        let body = serde_json::to_vec(&json!({ "choices": [] })).unwrap();
+        assert!(matches!(
+            parse_completion(&body),
+            Err(ScheduledTaskError::Parse(_))
+        ));
+    }
+}
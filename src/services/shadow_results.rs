@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        DbPool, DbResult,
+        repos::{ListParams, ListResult},
+    },
+    models::{ShadowReportRow, ShadowResult, ShadowResultFilter},
+};
+
+/// Service layer for recorded shadow traffic
+#[derive(Clone)]
+pub struct ShadowResultService {
+    db: Arc<DbPool>,
+}
+
+impl ShadowResultService {
+    pub fn new(db: Arc<DbPool>) -> Self {
+        Self { db }
+    }
+
+    /// Get a shadow result by ID
+    pub async fn get_by_id(&self, id: Uuid) -> DbResult<Option<ShadowResult>> {
+        self.db.shadow_results().get_by_id(id).await
+    }
+
+    /// List shadow results, newest first
+    pub async fn list(
+        &self,
+        filter: &ShadowResultFilter,
+        params: ListParams,
+    ) -> DbResult<ListResult<ShadowResult>> {
+        self.db.shadow_results().list(filter, params).await
+    }
+
+    /// Compare primary and shadow responses per rule and provider/model pair
+    pub async fn report(&self, filter: &ShadowResultFilter) -> DbResult<Vec<ShadowReportRow>> {
+        self.db.shadow_results().report(filter).await
+    }
+
+    /// Delete shadow results, returning how many were removed
+    pub async fn delete(&self, filter: &ShadowResultFilter) -> DbResult<u64> {
+        self.db.shadow_results().delete(filter).await
+    }
+}
@@ -0,0 +1,388 @@
+//! Shadow traffic for provider migrations.
+//!
+//! Rules in `[features.shadow_traffic]` mirror a sample of non-streaming
+//! chat completions to a candidate provider/model once the client has been
+//! answered. Both responses, their latency and cost land in `shadow_results`,
+//! so a migration (say OpenAI to Azure OpenAI) can be judged on real traffic
+//! before any client is switched over.
+//!
+//! The client never waits on or sees the shadow call. Like a replay, it is a
+//! single upstream call with no fallback chain, and it is not recorded as
+//! usage. At most `max_in_flight` run at once per node; requests beyond that
+//! are simply not mirrored.
+
+use std::{sync::Arc, time::Duration};
+
+use serde_json::Value;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use super::replay::{diff_lines, similarity};
+use crate::{
+    AppState,
+    api_types::CreateChatCompletionPayload,
+    config::{ShadowRule, ShadowTrafficConfig},
+    models::CreateShadowResult,
+    providers::Tenant,
+    routes::execution::{ChatCompletionExecutor, execute_provider},
+    routing::{resolver, route_model_extended},
+};
+
+/// Mirrors matching requests per `[features.shadow_traffic]`.
+pub struct ShadowTraffic {
+    config: ShadowTrafficConfig,
+    permits: Arc<Semaphore>,
+}
+
+/// A request picked for mirroring, holding its in-flight slot.
+pub struct ShadowSelection {
+    rule: ShadowRule,
+    permit: OwnedSemaphorePermit,
+}
+
+/// The response the client received.
+pub struct PrimaryResponse<'a> {
+    pub provider: &'a str,
+    pub model: &'a str,
+    pub status: u16,
+    pub latency_ms: u64,
+    pub body: &'a [u8],
+    /// Cost computed by the gateway, from `X-Cost-Microcents`
+    pub cost_microcents: Option<i64>,
+}
+
+impl ShadowTraffic {
+    /// `None` when no request would be mirrored.
+    pub fn new(config: &ShadowTrafficConfig) -> Option<Self> {
+        config.is_active().then(|| Self {
+            config: config.clone(),
+            permits: Arc::new(Semaphore::new(config.max_in_flight)),
+        })
+    }
+
+    /// Decide whether to mirror a request for `requested` (as the client
+    /// sent it) that routed to `provider`/`model`.
+    ///
+    /// The first matching rule applies, subject to its sample rate and a
+    /// free in-flight slot.
+    pub fn select(
+        &self,
+        requested: Option<&str>,
+        provider: &str,
+        model: &str,
+    ) -> Option<ShadowSelection> {
+        let routed = format!("{provider}/{model}");
+        let rule = self
+            .config
+            .rules
+            .iter()
+            .find(|rule| requested.is_some_and(|m| rule.matches(m)) || rule.matches(&routed))?;
+        if rule.sample_rate < 1.0 && rand::random::<f64>() >= rule.sample_rate {
+            return None;
+        }
+        let Ok(permit) = self.permits.clone().try_acquire_owned() else {
+            tracing::debug!(rule = %rule.name, "Shadow traffic at capacity, not mirroring");
+            return None;
+        };
+        Some(ShadowSelection {
+            rule: rule.clone(),
+            permit,
+        })
+    }
+
+    /// Send `payload` to the selected rule's target in the background and
+    /// record the comparison with `primary`.
+    pub fn mirror(
+        &self,
+        state: &AppState,
+        selection: ShadowSelection,
+        payload: CreateChatCompletionPayload,
+        tenant: Tenant,
+        primary: PrimaryResponse<'_>,
+    ) {
+        let Some(db) = state.db.clone() else {
+            return;
+        };
+        let primary_body: Value = serde_json::from_slice(primary.body).unwrap_or(Value::Null);
+        let (primary_input_tokens, primary_output_tokens) = usage_tokens(&primary_body);
+        let max_output_bytes = self.config.max_output_bytes;
+        let record = CreateShadowResult {
+            rule: selection.rule.name.clone(),
+            org_id: tenant.org_id,
+            project_id: tenant.project_id,
+            primary_provider: primary.provider.to_string(),
+            primary_model: primary.model.to_string(),
+            primary_status: i32::from(primary.status),
+            primary_latency_ms: primary.latency_ms as i64,
+            primary_input_tokens,
+            primary_output_tokens,
+            primary_cost_microcents: primary.cost_microcents,
+            primary_output: output_text(&primary_body),
+            ..Default::default()
+        };
+        let timeout = Duration::from_secs(self.config.timeout_secs);
+
+        let state = state.clone();
+        let tracker = state.task_tracker.clone();
+        tracker.spawn(async move {
+            let ShadowSelection { rule, permit } = selection;
+            let mut record = call_target(&state, &rule, payload, &tenant, record, timeout).await;
+            drop(permit);
+
+            if record.shadow_error.is_none()
+                && (200..300).contains(&record.primary_status)
+                && record
+                    .shadow_status
+                    .is_some_and(|s| (200..300).contains(&s))
+            {
+                record.similarity = Some(similarity(&diff_lines(
+                    &record.primary_output,
+                    &record.shadow_output,
+                )));
+            }
+            truncate_output(&mut record.primary_output, max_output_bytes);
+            truncate_output(&mut record.shadow_output, max_output_bytes);
+
+            if let Err(e) = db.shadow_results().create(record).await {
+                tracing::warn!(rule = %rule.name, error = %e, "Failed to record shadow result");
+            }
+        });
+    }
+}
+
+/// Route and run the shadow call, filling in the shadow side of `record`.
+async fn call_target(
+    state: &AppState,
+    rule: &ShadowRule,
+    mut payload: CreateChatCompletionPayload,
+    tenant: &Tenant,
+    mut record: CreateShadowResult,
+    timeout: Duration,
+) -> CreateShadowResult {
+    let (provider, model) = rule
+        .target
+        .split_once('/')
+        .unwrap_or(("", rule.target.as_str()));
+    record.shadow_provider = provider.to_string();
+    record.shadow_model = model.to_string();
+
+    let resolved = match route_model_extended(Some(&rule.target), &state.config.providers) {
+        Ok(routed) => {
+            resolver::resolve_to_provider(
+                routed,
+                state.db.as_ref(),
+                state.cache.as_ref(),
+                state.secrets.as_ref(),
+                &state.provider_settings,
+                None,
+            )
+            .await
+        }
+        Err(e) => {
+            record.shadow_error = Some(format!("routing failed: {e}"));
+            return record;
+        }
+    };
+    let resolved = match resolved {
+        Ok(resolved) => resolved,
+        Err(e) => {
+            record.shadow_error = Some(format!("routing failed: {e}"));
+            return record;
+        }
+    };
+    record.shadow_provider = resolved.provider_name.clone();
+    record.shadow_model = resolved.model.clone();
+
+    payload.model = Some(resolved.model.clone());
+    payload.models = None;
+    payload.stream = false;
+    payload.stream_options = None;
+
+    let started = std::time::Instant::now();
+    let call = async {
+        let response = execute_provider::<ChatCompletionExecutor>(
+            state,
+            &resolved.provider_name,
+            &resolved.provider_config,
+            payload,
+            tenant,
+        )
+        .await
+        .map_err(|e| format!("provider call failed: {e:?}"))?;
+        let status = response.status().as_u16();
+        let bytes = axum::body::to_bytes(
+            response.into_body(),
+            state.config.server.max_response_body_bytes,
+        )
+        .await
+        .map_err(|e| format!("failed to read body: {e}"))?;
+        Ok::<_, String>((status, bytes))
+    };
+    let (status, bytes) = match tokio::time::timeout(timeout, call).await {
+        Ok(Ok(response)) => response,
+        Ok(Err(e)) => {
+            record.shadow_error = Some(e);
+            return record;
+        }
+        Err(_) => {
+            record.shadow_error = Some(format!("timed out after {}s", timeout.as_secs()));
+            return record;
+        }
+    };
+    record.shadow_latency_ms = Some(started.elapsed().as_millis() as i64);
+    record.shadow_status = Some(i32::from(status));
+
+    let body: Value = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    let (input_tokens, output_tokens) = usage_tokens(&body);
+    record.shadow_input_tokens = input_tokens;
+    record.shadow_output_tokens = output_tokens;
+    record.shadow_cost_microcents = input_tokens.zip(output_tokens).and_then(|(i, o)| {
+        state
+            .pricing
+            .calculate_cost(&resolved.provider_name, &resolved.model, i, o)
+            .map(|(cost, _)| cost)
+    });
+    record.shadow_output = output_text(&body);
+    if !(200..300).contains(&status) {
+        record.shadow_error = body
+            .get("error")
+            .filter(|e| !e.is_null())
+            .map(|e| e.to_string());
+    }
+    record
+}
+
+/// Flatten a chat completion to comparable text: each choice's message
+/// content, then one `tool_call` line per tool call.
+fn output_text(body: &Value) -> String {
+    let mut lines = Vec::new();
+    for choice in body
+        .get("choices")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let Some(message) = choice.get("message") else {
+            continue;
+        };
+        if let Some(content) = message.get("content").and_then(Value::as_str) {
+            lines.push(content.to_string());
+        }
+        for call in message
+            .get("tool_calls")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            let function = call.get("function");
+            let name = function
+                .and_then(|f| f.get("name"))
+                .and_then(Value::as_str)
+                .unwrap_or("");
+            let args = function
+                .and_then(|f| f.get("arguments"))
+                .and_then(Value::as_str)
+                .unwrap_or("");
+            lines.push(format!("tool_call: {name} {args}"));
+        }
+    }
+    lines.join("\n")
+}
+
+fn usage_tokens(body: &Value) -> (Option<i64>, Option<i64>) {
+    let usage = body.get("usage");
+    let field = |name: &str| usage.and_then(|u| u.get(name)).and_then(Value::as_i64);
+    (field("prompt_tokens"), field("completion_tokens"))
+}
+
+fn truncate_output(text: &mut String, max_bytes: usize) {
+    if text.len() <= max_bytes {
+        return;
+    }
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    text.truncate(end);
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn config(sample_rate: f64, max_in_flight: usize) -> ShadowTrafficConfig {
+        ShadowTrafficConfig {
+            rules: vec![ShadowRule {
+                name: "azure-migration".into(),
+                models: vec!["openai/gpt-4o*".into()],
+                target: "azure/gpt-4o".into(),
+                sample_rate,
+            }],
+            max_in_flight,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_select_matches_requested_or_routed_model() {
+        let shadow = ShadowTraffic::new(&config(1.0, 4)).unwrap();
+        assert!(
+            shadow
+                .select(Some("openai/gpt-4o"), "openai", "gpt-4o")
+                .is_some()
+        );
+        // Default-provider request: the routed form matches
+        assert!(
+            shadow
+                .select(Some("gpt-4o-mini"), "openai", "gpt-4o-mini")
+                .is_some()
+        );
+        assert!(
+            shadow
+                .select(Some("anthropic/claude"), "anthropic", "claude")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_select_respects_capacity() {
+        let shadow = ShadowTraffic::new(&config(1.0, 1)).unwrap();
+        let held = shadow.select(None, "openai", "gpt-4o").unwrap();
+        assert!(shadow.select(None, "openai", "gpt-4o").is_none());
+        drop(held);
+        assert!(shadow.select(None, "openai", "gpt-4o").is_some());
+    }
+
+    #[test]
+    fn test_inactive_config() {
+        assert!(ShadowTraffic::new(&ShadowTrafficConfig::default()).is_none());
+        let mut disabled = config(1.0, 1);
+        disabled.enabled = false;
+        assert!(ShadowTraffic::new(&disabled).is_none());
+    }
+
+    #[test]
+    fn test_output_text_and_usage() {
+        let body = json!({
+            "choices": [{"message": {
+                "content": "Hello",
+                "tool_calls": [{"function": {"name": "lookup", "arguments": "{}"}}]
+            }}],
+            "usage": {"prompt_tokens": 12, "completion_tokens": 3}
+        });
+        assert_eq!(output_text(&body), "Hello\ntool_call: lookup {}");
+        assert_eq!(usage_tokens(&body), (Some(12), Some(3)));
+        assert_eq!(output_text(&Value::Null), "");
+    }
+
+    #[test]
+    fn test_truncate_output_on_char_boundary() {
+        let mut text = "héllo".to_string();
+        truncate_output(&mut text, 2);
+        assert_eq!(text, "h");
+        let mut short = "hi".to_string();
+        truncate_output(&mut short, 10);
+        assert_eq!(short, "hi");
+    }
+}
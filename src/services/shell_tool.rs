@@ -2498,6 +2498,20 @@ impl ServerExecutedTool for ShellExecutor {
                     tool_results_count: None,
                     tool_runtime_seconds: Some(duration_secs),
                     tool_exit_code: final_exit,
+                    jwt_subject: None,
+                    error_code: None,
+                    structured_output_repairs: None,
+                    context_original_tokens: None,
+                    context_compressed_tokens: None,
+                    degraded_from_model: None,
+                    degradation_reason: None,
+                    ttft_ms: None,
+                    inter_token_latency_ms: None,
+                    output_tokens_per_second: None,
+                    idempotency_key: None,
+                    provider_attempts: None,
+                    suspected_duplicate_attempts: None,
+                    tags: None,
                 });
             }
             #[cfg(not(feature = "concurrency"))]
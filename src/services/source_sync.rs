@@ -0,0 +1,955 @@
+//! Incremental sync of external document sources into vector stores.
+//!
+//! Sources are defined in `[features.vector_store_sync]` and run by the
+//! `vector_store_sync` background job. Each run:
+//!
+//! 1. Lists the source and the version of every document in it (S3 ETag,
+//!    Git blob hash, Confluence page version).
+//! 2. Compares the listing with the versions ingested by the previous run,
+//!    stored in `vector_store_sync_items`.
+//! 3. Downloads only new and changed documents, uploads them through the
+//!    Files API and attaches them to the vector store for processing. The
+//!    file holding the previous version is detached.
+//! 4. Detaches files for documents that are no longer in the source.
+//!
+//! Detached files are soft-deleted; the vector store cleanup job removes
+//! their chunks and the orphaned files.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use reqwest::Client;
+use serde::Deserialize;
+use thiserror::Error;
+use uuid::Uuid;
+
+#[cfg(any(
+    feature = "document-extraction-basic",
+    feature = "document-extraction-full"
+))]
+use super::DocumentProcessor;
+use super::{FilesService, FilesServiceError, VectorStoresService};
+use crate::{
+    config::{SyncConnector, VectorStoreSyncSource},
+    db::{DbError, DbPool},
+    models::{
+        AddFileToVectorStore, CreateVectorStoreSync, FilePurpose, VectorStore, VectorStoreStatus,
+        VectorStoreSync, VectorStoreSyncCounts, VectorStoreSyncItem, VectorStoreSyncStatus,
+    },
+};
+
+/// Page size when listing Confluence pages.
+const CONFLUENCE_PAGE_SIZE: usize = 100;
+
+#[derive(Debug, Error)]
+pub enum SourceSyncError {
+    #[error("Database error: {0}")]
+    Database(#[from] DbError),
+
+    #[error("Source error: {0}")]
+    Source(String),
+
+    #[error("File error: {0}")]
+    Files(#[from] FilesServiceError),
+
+    #[error("Vector store error: {0}")]
+    VectorStore(String),
+}
+
+/// A document as reported by a connector's listing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceDocument {
+    /// Stable key within the source (object key, repository path, page ID).
+    pub key: String,
+    /// Filename given to the uploaded file. Its extension decides how the
+    /// document processor extracts text.
+    pub filename: String,
+    /// Connector-specific version; a different value means the content changed.
+    pub version: String,
+    /// Size in bytes when the listing reports it.
+    pub size: Option<u64>,
+}
+
+/// What a run should do with each document, computed from the listing and
+/// the items recorded by the previous run.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SyncPlan<'a> {
+    pub added: Vec<&'a SourceDocument>,
+    pub updated: Vec<(&'a SourceDocument, &'a VectorStoreSyncItem)>,
+    pub deleted: Vec<&'a VectorStoreSyncItem>,
+    pub unchanged: usize,
+}
+
+impl<'a> SyncPlan<'a> {
+    pub fn new(documents: &'a [SourceDocument], previous: &'a [VectorStoreSyncItem]) -> Self {
+        let previous_by_key: HashMap<&str, &VectorStoreSyncItem> = previous
+            .iter()
+            .map(|item| (item.source_key.as_str(), item))
+            .collect();
+        let mut plan = Self::default();
+        let mut seen = HashSet::new();
+
+        for doc in documents {
+            if !seen.insert(doc.key.as_str()) {
+                continue;
+            }
+            match previous_by_key.get(doc.key.as_str()) {
+                None => plan.added.push(doc),
+                Some(item) if item.version == doc.version => plan.unchanged += 1,
+                Some(item) => plan.updated.push((doc, item)),
+            }
+        }
+        plan.deleted = previous
+            .iter()
+            .filter(|item| !seen.contains(item.source_key.as_str()))
+            .collect();
+        plan
+    }
+}
+
+/// Runs sync passes for configured sources.
+#[derive(Clone)]
+pub struct SourceSyncService {
+    db: Arc<DbPool>,
+    files: FilesService,
+    vector_stores: VectorStoresService,
+    http_client: Client,
+    max_file_bytes: u64,
+    #[cfg(any(
+        feature = "document-extraction-basic",
+        feature = "document-extraction-full"
+    ))]
+    document_processor: Option<Arc<DocumentProcessor>>,
+}
+
+impl SourceSyncService {
+    pub fn new(
+        db: Arc<DbPool>,
+        files: FilesService,
+        vector_stores: VectorStoresService,
+        http_client: Client,
+        max_file_bytes: u64,
+    ) -> Self {
+        Self {
+            db,
+            files,
+            vector_stores,
+            http_client,
+            max_file_bytes,
+            #[cfg(any(
+                feature = "document-extraction-basic",
+                feature = "document-extraction-full"
+            ))]
+            document_processor: None,
+        }
+    }
+
+    /// Process ingested files with this processor. Without one, synced files
+    /// stay `in_progress` until a file processing worker picks them up.
+    #[cfg(any(
+        feature = "document-extraction-basic",
+        feature = "document-extraction-full"
+    ))]
+    pub fn with_document_processor(mut self, processor: Arc<DocumentProcessor>) -> Self {
+        self.document_processor = Some(processor);
+        self
+    }
+
+    /// Run one sync pass for `source` and record it in `vector_store_syncs`.
+    ///
+    /// Only fails if the run itself couldn't be recorded; source and
+    /// ingestion errors are captured in the returned run.
+    pub async fn run(
+        &self,
+        source: &VectorStoreSyncSource,
+    ) -> Result<VectorStoreSync, SourceSyncError> {
+        let repo = self.db.vector_store_syncs();
+        let mut sync = repo
+            .create(CreateVectorStoreSync {
+                vector_store_id: source.vector_store_id,
+                source_name: source.name.clone(),
+                connector: source.connector.as_str().to_string(),
+            })
+            .await?;
+
+        let mut counts = VectorStoreSyncCounts::default();
+        let (status, error) = match self.sync(source, &mut counts).await {
+            Ok(last_error) => {
+                let applied = counts.files_added
+                    + counts.files_updated
+                    + counts.files_deleted
+                    + counts.files_unchanged;
+                let status = match (counts.files_failed, applied) {
+                    (0, _) => VectorStoreSyncStatus::Succeeded,
+                    (_, 0) => VectorStoreSyncStatus::Failed,
+                    _ => VectorStoreSyncStatus::PartiallySucceeded,
+                };
+                (status, last_error)
+            }
+            Err(e) => (VectorStoreSyncStatus::Failed, Some(e.to_string())),
+        };
+
+        repo.finish(sync.id, status, &counts, error.as_deref())
+            .await?;
+
+        sync.status = status;
+        sync.files_added = counts.files_added;
+        sync.files_updated = counts.files_updated;
+        sync.files_deleted = counts.files_deleted;
+        sync.files_unchanged = counts.files_unchanged;
+        sync.files_failed = counts.files_failed;
+        sync.error = error;
+        sync.finished_at = Some(chrono::Utc::now());
+        Ok(sync)
+    }
+
+    /// Apply the source to its vector store. Returns the last per-document
+    /// error, if any; errors that stop the whole run are returned as `Err`.
+    async fn sync(
+        &self,
+        source: &VectorStoreSyncSource,
+        counts: &mut VectorStoreSyncCounts,
+    ) -> Result<Option<String>, SourceSyncError> {
+        let vector_store = self
+            .vector_stores
+            .get_by_id(source.vector_store_id)
+            .await?
+            .ok_or_else(|| {
+                SourceSyncError::VectorStore(format!(
+                    "vector store {} not found",
+                    source.vector_store_id
+                ))
+            })?;
+        if vector_store.status == VectorStoreStatus::Expired {
+            return Err(SourceSyncError::VectorStore(format!(
+                "vector store {} has expired",
+                vector_store.id
+            )));
+        }
+
+        let snapshot = self.open(&source.connector).await?;
+        let documents: Vec<SourceDocument> = snapshot
+            .list(&self.http_client, &source.connector)
+            .await?
+            .into_iter()
+            .filter(|doc| source.includes(&doc.filename))
+            .collect();
+
+        let repo = self.db.vector_store_syncs();
+        let previous = repo.list_items(vector_store.id, &source.name).await?;
+        let plan = SyncPlan::new(&documents, &previous);
+        counts.files_unchanged = plan.unchanged as i64;
+
+        let mut last_error = None;
+
+        for doc in plan.added {
+            match self.ingest(&snapshot, source, &vector_store, doc).await {
+                Ok(()) => counts.files_added += 1,
+                Err(e) => {
+                    counts.files_failed += 1;
+                    last_error = Some(format!("{}: {e}", doc.key));
+                }
+            }
+        }
+
+        for (doc, item) in plan.updated {
+            let result = async {
+                self.ingest(&snapshot, source, &vector_store, doc).await?;
+                self.detach(vector_store.id, item.file_id).await
+            }
+            .await;
+            match result {
+                Ok(()) => counts.files_updated += 1,
+                Err(e) => {
+                    counts.files_failed += 1;
+                    last_error = Some(format!("{}: {e}", doc.key));
+                }
+            }
+        }
+
+        for item in plan.deleted {
+            let result = async {
+                self.detach(vector_store.id, item.file_id).await?;
+                repo.delete_item(vector_store.id, &source.name, &item.source_key)
+                    .await?;
+                Ok::<_, SourceSyncError>(())
+            }
+            .await;
+            match result {
+                Ok(()) => counts.files_deleted += 1,
+                Err(e) => {
+                    counts.files_failed += 1;
+                    last_error = Some(format!("{}: {e}", item.source_key));
+                }
+            }
+        }
+
+        Ok(last_error)
+    }
+
+    /// Download a document, upload it through the Files API, attach it to
+    /// the vector store and record its version.
+    async fn ingest(
+        &self,
+        snapshot: &Snapshot,
+        source: &VectorStoreSyncSource,
+        vector_store: &VectorStore,
+        doc: &SourceDocument,
+    ) -> Result<(), SourceSyncError> {
+        if let Some(size) = doc.size
+            && size > self.max_file_bytes
+        {
+            return Err(SourceSyncError::Source(format!(
+                "{size} bytes exceeds max_file_bytes ({})",
+                self.max_file_bytes
+            )));
+        }
+
+        let data = snapshot
+            .fetch(&self.http_client, &source.connector, doc)
+            .await?;
+        if data.len() as u64 > self.max_file_bytes {
+            return Err(SourceSyncError::Source(format!(
+                "{} bytes exceeds max_file_bytes ({})",
+                data.len(),
+                self.max_file_bytes
+            )));
+        }
+
+        let input = FilesService::create_file_input(
+            vector_store.owner_type,
+            vector_store.owner_id,
+            doc.filename.clone(),
+            FilePurpose::Assistants,
+            None,
+            data,
+            self.files.configured_backend(),
+        );
+        let file = self.files.upload(input).await?;
+
+        let attributes = HashMap::from([
+            ("source".to_string(), serde_json::json!(source.name)),
+            ("source_key".to_string(), serde_json::json!(doc.key)),
+        ]);
+        let vector_store_file = self
+            .vector_stores
+            .add_file(AddFileToVectorStore {
+                vector_store_id: vector_store.id,
+                file_id: file.id,
+                chunking_strategy: None,
+                attributes: Some(attributes),
+            })
+            .await?;
+
+        #[cfg(any(
+            feature = "document-extraction-basic",
+            feature = "document-extraction-full"
+        ))]
+        if let Some(processor) = &self.document_processor
+            && let Err(e) = processor
+                .clone()
+                .schedule_processing(vector_store_file.internal_id)
+                .await
+        {
+            tracing::error!(
+                error = %e,
+                internal_id = %vector_store_file.internal_id,
+                "Failed to schedule processing of synced file"
+            );
+        }
+        #[cfg(not(any(
+            feature = "document-extraction-basic",
+            feature = "document-extraction-full"
+        )))]
+        let _ = vector_store_file;
+
+        self.db
+            .vector_store_syncs()
+            .upsert_item(
+                vector_store.id,
+                &source.name,
+                &VectorStoreSyncItem {
+                    source_key: doc.key.clone(),
+                    version: doc.version.clone(),
+                    file_id: file.id,
+                },
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Detach a previously ingested file from the vector store. Already
+    /// detached files (e.g. removed through the API) are ignored.
+    async fn detach(&self, vector_store_id: Uuid, file_id: Uuid) -> Result<(), SourceSyncError> {
+        if let Some(existing) = self
+            .vector_stores
+            .find_by_file_id(vector_store_id, file_id)
+            .await?
+        {
+            self.vector_stores.remove_file(existing.internal_id).await?;
+        }
+        Ok(())
+    }
+
+    async fn open(&self, connector: &SyncConnector) -> Result<Snapshot, SourceSyncError> {
+        match connector {
+            #[cfg(feature = "s3-storage")]
+            SyncConnector::S3 {
+                region,
+                endpoint,
+                access_key_id,
+                secret_access_key,
+                force_path_style,
+                ..
+            } => {
+                let mut sdk_config_builder =
+                    aws_config::defaults(aws_config::BehaviorVersion::latest());
+                if let Some(region) = region {
+                    sdk_config_builder =
+                        sdk_config_builder.region(aws_config::Region::new(region.clone()));
+                }
+                if let (Some(access_key), Some(secret_key)) = (access_key_id, secret_access_key) {
+                    let credentials = aws_credential_types::Credentials::new(
+                        access_key.clone(),
+                        secret_key.clone(),
+                        None,
+                        None,
+                        "hadrian-config",
+                    );
+                    sdk_config_builder = sdk_config_builder.credentials_provider(credentials);
+                }
+                let sdk_config = sdk_config_builder.load().await;
+                let mut s3_config_builder = aws_sdk_s3::config::Builder::from(&sdk_config);
+                if let Some(endpoint) = endpoint {
+                    s3_config_builder = s3_config_builder.endpoint_url(endpoint);
+                }
+                if *force_path_style {
+                    s3_config_builder = s3_config_builder.force_path_style(true);
+                }
+                Ok(Snapshot::S3(aws_sdk_s3::Client::from_conf(
+                    s3_config_builder.build(),
+                )))
+            }
+            #[cfg(not(feature = "s3-storage"))]
+            SyncConnector::S3 { .. } => Err(SourceSyncError::Source(
+                "the s3 connector requires the 's3-storage' feature".to_string(),
+            )),
+            SyncConnector::Git { url, branch, .. } => {
+                let checkout = GitCheckout::clone(url.clone(), branch.clone()).await?;
+                Ok(Snapshot::Git(checkout))
+            }
+            SyncConnector::Confluence { .. } => Ok(Snapshot::Confluence),
+        }
+    }
+}
+
+/// Connector state shared between listing a source and fetching its
+/// documents within one run.
+enum Snapshot {
+    #[cfg(feature = "s3-storage")]
+    S3(aws_sdk_s3::Client),
+    Git(GitCheckout),
+    Confluence,
+}
+
+impl Snapshot {
+    async fn list(
+        &self,
+        http_client: &Client,
+        connector: &SyncConnector,
+    ) -> Result<Vec<SourceDocument>, SourceSyncError> {
+        match (self, connector) {
+            #[cfg(feature = "s3-storage")]
+            (Self::S3(client), SyncConnector::S3 { bucket, prefix, .. }) => {
+                list_s3(client, bucket, prefix).await
+            }
+            (Self::Git(checkout), SyncConnector::Git { path, .. }) => {
+                checkout.list(path.clone()).await
+            }
+            (
+                Self::Confluence,
+                SyncConnector::Confluence {
+                    base_url,
+                    space,
+                    email,
+                    api_token,
+                },
+            ) => {
+                let auth = ConfluenceAuth::new(email.as_deref(), api_token.as_deref());
+                list_confluence(http_client, base_url, space, &auth).await
+            }
+            _ => Err(SourceSyncError::Source("connector mismatch".to_string())),
+        }
+    }
+
+    async fn fetch(
+        &self,
+        http_client: &Client,
+        connector: &SyncConnector,
+        doc: &SourceDocument,
+    ) -> Result<Vec<u8>, SourceSyncError> {
+        match (self, connector) {
+            #[cfg(feature = "s3-storage")]
+            (Self::S3(client), SyncConnector::S3 { bucket, .. }) => {
+                let output = client
+                    .get_object()
+                    .bucket(bucket)
+                    .key(&doc.key)
+                    .send()
+                    .await
+                    .map_err(|e| SourceSyncError::Source(format!("S3 get failed: {e}")))?;
+                let bytes = output
+                    .body
+                    .collect()
+                    .await
+                    .map_err(|e| SourceSyncError::Source(format!("S3 read failed: {e}")))?;
+                Ok(bytes.into_bytes().to_vec())
+            }
+            (Self::Git(checkout), SyncConnector::Git { .. }) => checkout.read(&doc.key).await,
+            (
+                Self::Confluence,
+                SyncConnector::Confluence {
+                    base_url,
+                    email,
+                    api_token,
+                    ..
+                },
+            ) => {
+                let auth = ConfluenceAuth::new(email.as_deref(), api_token.as_deref());
+                fetch_confluence_page(http_client, base_url, &auth, doc).await
+            }
+            _ => Err(SourceSyncError::Source("connector mismatch".to_string())),
+        }
+    }
+}
+
+/// Last path segment of a key.
+fn filename_of(key: &str) -> String {
+    key.rsplit('/').next().unwrap_or(key).to_string()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// S3
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(feature = "s3-storage")]
+async fn list_s3(
+    client: &aws_sdk_s3::Client,
+    bucket: &str,
+    prefix: &str,
+) -> Result<Vec<SourceDocument>, SourceSyncError> {
+    let mut documents = Vec::new();
+    let mut continuation_token = None;
+
+    loop {
+        let output = client
+            .list_objects_v2()
+            .bucket(bucket)
+            .prefix(prefix)
+            .set_continuation_token(continuation_token)
+            .send()
+            .await
+            .map_err(|e| SourceSyncError::Source(format!("S3 list failed: {e}")))?;
+
+        for object in output.contents() {
+            let (Some(key), Some(etag)) = (object.key(), object.e_tag()) else {
+                continue;
+            };
+            // Skip "directory" placeholder objects
+            if key.ends_with('/') {
+                continue;
+            }
+            documents.push(SourceDocument {
+                key: key.to_string(),
+                filename: filename_of(key),
+                version: etag.trim_matches('"').to_string(),
+                size: object.size().and_then(|s| u64::try_from(s).ok()),
+            });
+        }
+
+        match output.next_continuation_token() {
+            Some(token) if output.is_truncated().unwrap_or(false) => {
+                continuation_token = Some(token.to_string());
+            }
+            _ => break,
+        }
+    }
+
+    Ok(documents)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Git
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A shallow clone in a temporary directory, removed on drop.
+struct GitCheckout {
+    dir: PathBuf,
+}
+
+impl GitCheckout {
+    async fn clone(url: String, branch: Option<String>) -> Result<Self, SourceSyncError> {
+        let dir = std::env::temp_dir().join(format!("hadrian-sync-{}", Uuid::new_v4()));
+        let checkout = Self { dir };
+        let target = checkout.dir.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut args = vec![
+                "clone".to_string(),
+                "--depth".to_string(),
+                "1".to_string(),
+                "--single-branch".to_string(),
+            ];
+            if let Some(branch) = branch {
+                args.push("--branch".to_string());
+                args.push(branch);
+            }
+            args.push("--".to_string());
+            args.push(url);
+            args.push(target.to_string_lossy().into_owned());
+            run_git(None, &args)
+        })
+        .await
+        .map_err(|e| SourceSyncError::Source(format!("git clone task failed: {e}")))??;
+
+        Ok(checkout)
+    }
+
+    /// List blobs under `path` with their hashes and sizes.
+    async fn list(&self, path: Option<String>) -> Result<Vec<SourceDocument>, SourceSyncError> {
+        let dir = self.dir.clone();
+        let output = tokio::task::spawn_blocking(move || {
+            let mut args = vec![
+                "ls-tree".to_string(),
+                "-r".to_string(),
+                "-l".to_string(),
+                "-z".to_string(),
+                "HEAD".to_string(),
+            ];
+            if let Some(path) = path.filter(|p| !p.is_empty()) {
+                args.push("--".to_string());
+                args.push(path);
+            }
+            run_git(Some(&dir), &args)
+        })
+        .await
+        .map_err(|e| SourceSyncError::Source(format!("git ls-tree task failed: {e}")))??;
+
+        Ok(parse_ls_tree(&output))
+    }
+
+    async fn read(&self, key: &str) -> Result<Vec<u8>, SourceSyncError> {
+        // ls-tree only reports paths inside the checkout, but a key must never
+        // resolve outside it.
+        if Path::new(key).is_absolute() || key.split('/').any(|part| part == "..") {
+            return Err(SourceSyncError::Source(format!("invalid path '{key}'")));
+        }
+        let path = self.dir.join(key);
+        tokio::task::spawn_blocking(move || std::fs::read(&path))
+            .await
+            .map_err(|e| SourceSyncError::Source(format!("read task failed: {e}")))?
+            .map_err(|e| SourceSyncError::Source(format!("read failed: {e}")))
+    }
+}
+
+impl Drop for GitCheckout {
+    fn drop(&mut self) {
+        if self.dir.exists()
+            && let Err(e) = std::fs::remove_dir_all(&self.dir)
+        {
+            tracing::warn!(
+                error = %e,
+                dir = %self.dir.display(),
+                "Failed to remove sync checkout"
+            );
+        }
+    }
+}
+
+fn run_git(dir: Option<&Path>, args: &[String]) -> Result<String, SourceSyncError> {
+    let mut command = std::process::Command::new("git");
+    if let Some(dir) = dir {
+        command.current_dir(dir);
+    }
+    let output = command
+        .args(args)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .map_err(|e| SourceSyncError::Source(format!("failed to run git: {e}")))?;
+    if !output.status.success() {
+        return Err(SourceSyncError::Source(format!(
+            "git {} failed: {}",
+            args.first().map(String::as_str).unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Parse `git ls-tree -r -l -z` output: `<mode> <type> <hash> <size>\t<path>\0`.
+fn parse_ls_tree(output: &str) -> Vec<SourceDocument> {
+    output
+        .split('\0')
+        .filter_map(|entry| {
+            let (meta, path) = entry.split_once('\t')?;
+            let mut fields = meta.split_whitespace();
+            let _mode = fields.next()?;
+            if fields.next()? != "blob" {
+                return None;
+            }
+            let hash = fields.next()?;
+            let size = fields.next().and_then(|s| s.parse().ok());
+            Some(SourceDocument {
+                key: path.to_string(),
+                filename: filename_of(path),
+                version: hash.to_string(),
+                size,
+            })
+        })
+        .collect()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Confluence
+// ─────────────────────────────────────────────────────────────────────────────
+
+enum ConfluenceAuth {
+    Basic { email: String, token: String },
+    Bearer(String),
+    None,
+}
+
+impl ConfluenceAuth {
+    fn new(email: Option<&str>, api_token: Option<&str>) -> Self {
+        match (email, api_token) {
+            (Some(email), Some(token)) => Self::Basic {
+                email: email.to_string(),
+                token: token.to_string(),
+            },
+            (None, Some(token)) => Self::Bearer(token.to_string()),
+            _ => Self::None,
+        }
+    }
+
+    fn apply(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match self {
+            Self::Basic { email, token } => request.basic_auth(email, Some(token)),
+            Self::Bearer(token) => request.bearer_auth(token),
+            Self::None => request,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ConfluencePageList {
+    results: Vec<ConfluencePage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConfluencePage {
+    id: String,
+    title: String,
+    version: Option<ConfluenceVersion>,
+    body: Option<ConfluenceBody>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConfluenceVersion {
+    number: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConfluenceBody {
+    storage: ConfluenceStorage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConfluenceStorage {
+    value: String,
+}
+
+async fn list_confluence(
+    client: &Client,
+    base_url: &str,
+    space: &str,
+    auth: &ConfluenceAuth,
+) -> Result<Vec<SourceDocument>, SourceSyncError> {
+    let url = format!("{}/rest/api/content", base_url.trim_end_matches('/'));
+    let mut documents = Vec::new();
+    let mut start = 0;
+
+    loop {
+        let limit = CONFLUENCE_PAGE_SIZE.to_string();
+        let offset = start.to_string();
+        let request = client.get(&url).query(&[
+            ("spaceKey", space),
+            ("type", "page"),
+            ("status", "current"),
+            ("expand", "version"),
+            ("limit", limit.as_str()),
+            ("start", offset.as_str()),
+        ]);
+        let page: ConfluencePageList = auth
+            .apply(request)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| SourceSyncError::Source(format!("Confluence list failed: {e}")))?
+            .json()
+            .await
+            .map_err(|e| SourceSyncError::Source(format!("Confluence list failed: {e}")))?;
+
+        let fetched = page.results.len();
+        for result in page.results {
+            let Some(version) = result.version else {
+                continue;
+            };
+            documents.push(SourceDocument {
+                filename: format!("{}.html", sanitize_filename(&result.title)),
+                key: result.id,
+                version: version.number.to_string(),
+                size: None,
+            });
+        }
+
+        if fetched < CONFLUENCE_PAGE_SIZE {
+            break;
+        }
+        start += fetched;
+    }
+
+    Ok(documents)
+}
+
+async fn fetch_confluence_page(
+    client: &Client,
+    base_url: &str,
+    auth: &ConfluenceAuth,
+    doc: &SourceDocument,
+) -> Result<Vec<u8>, SourceSyncError> {
+    let url = format!(
+        "{}/rest/api/content/{}",
+        base_url.trim_end_matches('/'),
+        doc.key
+    );
+    let request = client.get(&url).query(&[("expand", "body.storage")]);
+    let page: ConfluencePage = auth
+        .apply(request)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| SourceSyncError::Source(format!("Confluence fetch failed: {e}")))?
+        .json()
+        .await
+        .map_err(|e| SourceSyncError::Source(format!("Confluence fetch failed: {e}")))?;
+
+    let body = page.body.map(|b| b.storage.value).unwrap_or_default();
+    Ok(format!(
+        "<html><head><title>{}</title></head><body>{}</body></html>",
+        escape_html(&page.title),
+        body
+    )
+    .into_bytes())
+}
+
+/// Replace characters that aren't safe in filenames.
+fn sanitize_filename(title: &str) -> String {
+    let name: String = title
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    let name = name.trim();
+    if name.is_empty() {
+        "page".to_string()
+    } else {
+        name.chars().take(200).collect()
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(key: &str, version: &str) -> SourceDocument {
+        SourceDocument {
+            key: key.to_string(),
+            filename: filename_of(key),
+            version: version.to_string(),
+            size: None,
+        }
+    }
+
+    fn item(key: &str, version: &str) -> VectorStoreSyncItem {
+        VectorStoreSyncItem {
+            source_key: key.to_string(),
+            version: version.to_string(),
+            file_id: Uuid::new_v4(),
+        }
+    }
+
+    #[test]
+    fn test_plan_classifies_documents() {
+        let documents = vec![
+            doc("a.md", "1"),
+            doc("b.md", "2"),
+            doc("c.md", "3"),
+            // Duplicate keys are only considered once
+            doc("a.md", "9"),
+        ];
+        let previous = vec![item("a.md", "1"), item("b.md", "1"), item("gone.md", "1")];
+
+        let plan = SyncPlan::new(&documents, &previous);
+        assert_eq!(plan.unchanged, 1);
+        assert_eq!(plan.added, vec![&documents[2]]);
+        assert_eq!(plan.updated.len(), 1);
+        assert_eq!(plan.updated[0].0.key, "b.md");
+        assert_eq!(plan.updated[0].1.version, "1");
+        assert_eq!(plan.deleted, vec![&previous[2]]);
+    }
+
+    #[test]
+    fn test_plan_empty_source_deletes_everything() {
+        let previous = vec![item("a.md", "1"), item("b.md", "1")];
+        let plan = SyncPlan::new(&[], &previous);
+        assert!(plan.added.is_empty());
+        assert!(plan.updated.is_empty());
+        assert_eq!(plan.deleted.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_ls_tree() {
+        let output = "100644 blob 3b18e512dba79e4c8300dd08aeb37f8e728b8dad      12\tdocs/intro.md\0\
+                      160000 commit 4b825dc642cb6eb9a060e54bf8d69288fbee4904       -\tvendor/lib\0\
+                      100644 blob e69de29bb2d1d6434b8b29ae775ad8c2e48c5391       0\tdocs/with space.txt\0";
+        let docs = parse_ls_tree(output);
+        assert_eq!(docs.len(), 2, "submodules are skipped");
+        assert_eq!(docs[0].key, "docs/intro.md");
+        assert_eq!(docs[0].filename, "intro.md");
+        assert_eq!(docs[0].version, "3b18e512dba79e4c8300dd08aeb37f8e728b8dad");
+        assert_eq!(docs[0].size, Some(12));
+        assert_eq!(docs[1].filename, "with space.txt");
+    }
+
+    #[test]
+    fn test_sanitize_filename() {
+        assert_eq!(sanitize_filename("Setup / Install"), "Setup _ Install");
+        assert_eq!(sanitize_filename("   "), "page");
+        assert_eq!(sanitize_filename("Q&A: FAQ?"), "Q&A_ FAQ_");
+    }
+}
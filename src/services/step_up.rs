@@ -0,0 +1,136 @@
+//! Service layer for admin TOTP authenticators used by step-up authentication.
+//!
+//! Enforcement (which routes need step-up, and how a verification is
+//! remembered between requests) lives in the admin middleware; this service
+//! only manages enrollments and checks codes.
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use super::FieldEncryptor;
+use crate::{
+    auth::totp,
+    db::{DbError, DbPool, DbResult},
+    models::AdminTotpEnrollment,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum StepUpError {
+    #[error("No TOTP authenticator is enrolled")]
+    NotEnrolled,
+    #[error("A TOTP authenticator is already enrolled; remove it before enrolling a new one")]
+    AlreadyEnrolled,
+    #[error("Invalid or already used TOTP code")]
+    InvalidCode,
+    #[error("Database error: {0}")]
+    Database(#[from] DbError),
+}
+
+/// Service layer for admin TOTP enrollment and verification.
+#[derive(Clone)]
+pub struct StepUpService {
+    db: Arc<DbPool>,
+    encryption: Option<Arc<FieldEncryptor>>,
+}
+
+impl StepUpService {
+    pub fn new(db: Arc<DbPool>) -> Self {
+        Self {
+            db,
+            encryption: None,
+        }
+    }
+
+    /// Encrypt TOTP secrets at rest with the given encryptor.
+    pub fn with_encryption(mut self, encryption: Option<Arc<FieldEncryptor>>) -> Self {
+        self.encryption = encryption;
+        self
+    }
+
+    /// Get a user's enrollment (pending or verified) with its secret decrypted.
+    pub async fn get(&self, user_id: Uuid) -> DbResult<Option<AdminTotpEnrollment>> {
+        let Some(mut enrollment) = self.db.admin_totp().get(user_id).await? else {
+            return Ok(None);
+        };
+        if let Some(enc) = &self.encryption {
+            enrollment.secret = enc.decrypt(&enrollment.secret)?;
+        }
+        Ok(Some(enrollment))
+    }
+
+    /// Whether the user has a confirmed authenticator.
+    pub async fn is_enrolled(&self, user_id: Uuid) -> DbResult<bool> {
+        Ok(self
+            .db
+            .admin_totp()
+            .get(user_id)
+            .await?
+            .is_some_and(|e| e.is_verified()))
+    }
+
+    /// Start enrollment and return the new base32 secret.
+    ///
+    /// Restarting a pending enrollment replaces its secret. A confirmed
+    /// authenticator must be removed first, so a stolen session alone can't
+    /// swap in an attacker's device.
+    pub async fn begin_enrollment(&self, user_id: Uuid) -> Result<String, StepUpError> {
+        if self.is_enrolled(user_id).await? {
+            return Err(StepUpError::AlreadyEnrolled);
+        }
+        let secret = totp::generate_secret();
+        let stored = match &self.encryption {
+            Some(enc) => enc.encrypt(&secret).map_err(DbError::from)?,
+            None => secret.clone(),
+        };
+        self.db
+            .admin_totp()
+            .upsert_pending(user_id, &stored)
+            .await?;
+        Ok(secret)
+    }
+
+    /// Confirm a pending enrollment with a code from the authenticator app.
+    pub async fn confirm_enrollment(&self, user_id: Uuid, code: &str) -> Result<(), StepUpError> {
+        let enrollment = self.get(user_id).await?.ok_or(StepUpError::NotEnrolled)?;
+        if enrollment.is_verified() {
+            return Err(StepUpError::AlreadyEnrolled);
+        }
+        let step = totp::verify(&enrollment.secret, code, Utc::now().timestamp(), None)
+            .ok_or(StepUpError::InvalidCode)?;
+        if !self.db.admin_totp().mark_verified(user_id, step).await? {
+            // Confirmed concurrently by another request
+            return Err(StepUpError::AlreadyEnrolled);
+        }
+        Ok(())
+    }
+
+    /// Check a code against a confirmed authenticator.
+    ///
+    /// Each code is accepted at most once: the matched time step is recorded
+    /// atomically, so a replayed or concurrently reused code fails.
+    pub async fn verify(&self, user_id: Uuid, code: &str) -> Result<(), StepUpError> {
+        let enrollment = self
+            .get(user_id)
+            .await?
+            .filter(|e| e.is_verified())
+            .ok_or(StepUpError::NotEnrolled)?;
+        let step = totp::verify(
+            &enrollment.secret,
+            code,
+            Utc::now().timestamp(),
+            enrollment.last_used_step,
+        )
+        .ok_or(StepUpError::InvalidCode)?;
+        if !self.db.admin_totp().record_use(user_id, step).await? {
+            return Err(StepUpError::InvalidCode);
+        }
+        Ok(())
+    }
+
+    /// Remove a user's authenticator (pending or confirmed).
+    pub async fn disable(&self, user_id: Uuid) -> DbResult<bool> {
+        self.db.admin_totp().delete(user_id).await
+    }
+}
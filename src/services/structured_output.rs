@@ -0,0 +1,274 @@
+//! Structured output enforcement for `response_format: json_schema`.
+//!
+//! Not every provider honors a JSON schema, and those that do can still
+//! return truncated or non-conforming output. With
+//! `[features.structured_outputs]` enabled, the chat completions handler
+//! checks the message content of non-streaming responses against the
+//! request's schema. When it doesn't match, the output and the validation
+//! errors are appended to the conversation and the request is sent again,
+//! up to the resolved number of repairs.
+//!
+//! Tokens spent on discarded attempts are folded into the returned
+//! response's `usage`, so the request is billed for every attempt.
+
+use serde_json::Value;
+
+use crate::api_types::{CreateChatCompletionPayload, Message, MessageContent, ResponseFormat};
+
+/// Request header overriding the number of repairs for one request.
+pub const MAX_REPAIRS_HEADER: &str = "X-Structured-Output-Max-Repairs";
+
+/// Response header with the number of repairs made. Also recorded in usage.
+pub const REPAIRS_HEADER: &str = "X-Structured-Output-Repairs";
+
+/// Response header saying whether the returned content matches the schema.
+pub const VALID_HEADER: &str = "X-Structured-Output-Valid";
+
+/// Validation errors reported back to the model per attempt.
+const MAX_REPORTED_ERRORS: usize = 10;
+
+/// A request's `json_schema`, compiled for validating responses.
+pub struct SchemaValidator {
+    #[cfg(feature = "response-validation")]
+    validator: jsonschema::Validator,
+}
+
+impl SchemaValidator {
+    /// Compile the schema in `payload.response_format`.
+    ///
+    /// `None` when the request doesn't ask for a JSON schema, `Err` when the
+    /// schema doesn't compile.
+    pub fn for_payload(payload: &CreateChatCompletionPayload) -> Option<Result<Self, String>> {
+        let Some(ResponseFormat::JsonSchema { json_schema }) = &payload.response_format else {
+            return None;
+        };
+        json_schema.schema.as_ref().map(Self::compile)
+    }
+
+    #[cfg(feature = "response-validation")]
+    fn compile(schema: &Value) -> Result<Self, String> {
+        jsonschema::validator_for(schema)
+            .map(|validator| Self { validator })
+            .map_err(|e| e.to_string())
+    }
+
+    #[cfg(not(feature = "response-validation"))]
+    fn compile(_schema: &Value) -> Result<Self, String> {
+        Err("schema validation requires the 'response-validation' feature".to_string())
+    }
+
+    /// Check message content against the schema. Empty when it matches.
+    pub fn validate(&self, content: &str) -> Vec<String> {
+        let value: Value = match serde_json::from_str(content) {
+            Ok(value) => value,
+            Err(e) => return vec![format!("content is not valid JSON: {e}")],
+        };
+        #[cfg(feature = "response-validation")]
+        {
+            self.validator
+                .iter_errors(&value)
+                .take(MAX_REPORTED_ERRORS)
+                .map(|e| {
+                    let path = e.instance_path.to_string();
+                    if path.is_empty() {
+                        e.to_string()
+                    } else {
+                        format!("{path}: {e}")
+                    }
+                })
+                .collect()
+        }
+        #[cfg(not(feature = "response-validation"))]
+        {
+            let _ = (value, MAX_REPORTED_ERRORS);
+            Vec::new()
+        }
+    }
+}
+
+/// The assistant message content of a chat completion body.
+///
+/// `None` for refusals and tool calls, which have nothing to validate.
+pub fn completion_content(body: &Value) -> Option<&str> {
+    let message = body.get("choices")?.get(0)?.get("message")?;
+    if message.get("refusal").is_some_and(|r| !r.is_null()) {
+        return None;
+    }
+    message.get("content")?.as_str()
+}
+
+/// Append the rejected output and its validation errors to the conversation.
+pub fn add_repair_prompt(
+    payload: &mut CreateChatCompletionPayload,
+    content: &str,
+    errors: &[String],
+) {
+    payload.messages.push(Message::Assistant {
+        content: Some(MessageContent::Text(content.to_string())),
+        name: None,
+        tool_calls: None,
+        refusal: None,
+        reasoning: None,
+    });
+    let errors = errors
+        .iter()
+        .map(|e| format!("- {e}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    payload.messages.push(Message::User {
+        content: MessageContent::Text(format!(
+            "Your previous response does not match the required JSON schema:\n{errors}\n\n\
+             Reply again with only a JSON value that matches the schema."
+        )),
+        name: None,
+    });
+}
+
+/// Token usage of attempts whose output was discarded.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DiscardedUsage {
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+}
+
+impl DiscardedUsage {
+    /// Count a discarded attempt's `usage`.
+    pub fn add(&mut self, body: &Value) {
+        let usage = body.get("usage");
+        let field = |name: &str| {
+            usage
+                .and_then(|u| u.get(name))
+                .and_then(Value::as_i64)
+                .unwrap_or(0)
+        };
+        self.prompt_tokens += field("prompt_tokens");
+        self.completion_tokens += field("completion_tokens");
+    }
+
+    /// Add the discarded tokens to the `usage` of the body being returned.
+    /// Returns whether the body was changed.
+    pub fn apply(&self, body: &mut Value) -> bool {
+        if *self == Self::default() {
+            return false;
+        }
+        let Some(usage) = body.get_mut("usage").and_then(Value::as_object_mut) else {
+            return false;
+        };
+        for (name, extra) in [
+            ("prompt_tokens", self.prompt_tokens),
+            ("completion_tokens", self.completion_tokens),
+            ("total_tokens", self.prompt_tokens + self.completion_tokens),
+        ] {
+            let current = usage.get(name).and_then(Value::as_i64).unwrap_or(0);
+            usage.insert(name.to_string(), Value::from(current + extra));
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn payload(response_format: Value) -> CreateChatCompletionPayload {
+        serde_json::from_value(json!({
+            "model": "gpt-4o",
+            "messages": [{"role": "user", "content": "Give me a person"}],
+            "response_format": response_format,
+        }))
+        .unwrap()
+    }
+
+    fn person_schema() -> Value {
+        json!({
+            "type": "json_schema",
+            "json_schema": {
+                "name": "person",
+                "schema": {
+                    "type": "object",
+                    "properties": {"name": {"type": "string"}, "age": {"type": "integer"}},
+                    "required": ["name", "age"]
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_for_payload_only_json_schema() {
+        assert!(SchemaValidator::for_payload(&payload(json!({"type": "json_object"}))).is_none());
+        assert!(SchemaValidator::for_payload(&payload(json!({"type": "text"}))).is_none());
+        assert!(SchemaValidator::for_payload(&payload(person_schema())).is_some());
+    }
+
+    #[cfg(feature = "response-validation")]
+    #[test]
+    fn test_validate_reports_errors() {
+        let validator = SchemaValidator::for_payload(&payload(person_schema()))
+            .unwrap()
+            .unwrap();
+        assert!(
+            validator
+                .validate(r#"{"name": "Ada", "age": 36}"#)
+                .is_empty()
+        );
+
+        let errors = validator.validate(r#"{"name": "Ada", "age": "36"}"#);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("/age: "), "{errors:?}");
+
+        let errors = validator.validate("Sure! Here is a person: Ada");
+        assert!(errors[0].starts_with("content is not valid JSON"));
+    }
+
+    #[cfg(feature = "response-validation")]
+    #[test]
+    fn test_invalid_schema_is_rejected() {
+        let result = SchemaValidator::for_payload(&payload(json!({
+            "type": "json_schema",
+            "json_schema": {"name": "bad", "schema": {"type": 12}}
+        })))
+        .unwrap();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_completion_content_skips_refusals() {
+        let body = json!({"choices": [{"message": {"content": "{}"}}]});
+        assert_eq!(completion_content(&body), Some("{}"));
+        let refusal = json!({"choices": [{"message": {"content": null, "refusal": "No"}}]});
+        assert_eq!(completion_content(&refusal), None);
+    }
+
+    #[test]
+    fn test_add_repair_prompt() {
+        let mut payload = payload(person_schema());
+        add_repair_prompt(&mut payload, "{}", &["missing name".to_string()]);
+        assert_eq!(payload.messages.len(), 3);
+        assert!(matches!(payload.messages[1], Message::Assistant { .. }));
+        let Message::User {
+            content: MessageContent::Text(text),
+            ..
+        } = &payload.messages[2]
+        else {
+            panic!("expected user message");
+        };
+        assert!(text.contains("- missing name"));
+    }
+
+    #[test]
+    fn test_discarded_usage_is_added() {
+        let mut discarded = DiscardedUsage::default();
+        let mut body =
+            json!({"usage": {"prompt_tokens": 10, "completion_tokens": 5, "total_tokens": 15}});
+        assert!(!discarded.apply(&mut body));
+
+        discarded.add(&json!({"usage": {"prompt_tokens": 8, "completion_tokens": 4}}));
+        assert!(discarded.apply(&mut body));
+        assert_eq!(
+            body["usage"],
+            json!({"prompt_tokens": 18, "completion_tokens": 9, "total_tokens": 27})
+        );
+    }
+}
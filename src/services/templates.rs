@@ -1,7 +1,8 @@
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
 use uuid::Uuid;
 
+use super::FieldEncryptor;
 use crate::{
     db::{DbPool, DbResult, ListParams, repos::ListResult},
     models::{CreateTemplate, Template, TemplateOwnerType, UpdateTemplate},
@@ -11,28 +12,60 @@ use crate::{
 #[derive(Clone)]
 pub struct TemplateService {
     db: Arc<DbPool>,
+    encryption: Option<Arc<FieldEncryptor>>,
 }
 
 impl TemplateService {
     pub fn new(db: Arc<DbPool>) -> Self {
-        Self { db }
+        Self {
+            db,
+            encryption: None,
+        }
+    }
+
+    /// Encrypt template content at rest with the given encryptor.
+    pub fn with_encryption(mut self, encryption: Option<Arc<FieldEncryptor>>) -> Self {
+        self.encryption = encryption;
+        self
+    }
+
+    fn decrypt(&self, mut template: Template) -> DbResult<Template> {
+        if let Some(enc) = &self.encryption {
+            template.content = enc.decrypt(&template.content)?;
+        }
+        Ok(template)
+    }
+
+    fn decrypt_list(&self, mut result: ListResult<Template>) -> DbResult<ListResult<Template>> {
+        if let Some(enc) = &self.encryption {
+            for template in &mut result.items {
+                template.content = enc.decrypt(&template.content)?;
+            }
+        }
+        Ok(result)
     }
 
     /// Create a new template
-    pub async fn create(&self, input: CreateTemplate) -> DbResult<Template> {
-        self.db.templates().create(input).await
+    pub async fn create(&self, mut input: CreateTemplate) -> DbResult<Template> {
+        if let Some(enc) = &self.encryption {
+            input.content = enc.encrypt(&input.content)?;
+        }
+        let template = self.db.templates().create(input).await?;
+        self.decrypt(template)
     }
 
     /// Get a template by ID
     pub async fn get_by_id(&self, id: Uuid) -> DbResult<Option<Template>> {
-        self.db.templates().get_by_id(id).await
+        let template = self.db.templates().get_by_id(id).await?;
+        template.map(|t| self.decrypt(t)).transpose()
     }
 
     /// Get a template by ID, scoped to a specific organization.
     ///
     /// Use this variant when org context is available to prevent cross-org access.
     pub async fn get_by_id_and_org(&self, id: Uuid, org_id: Uuid) -> DbResult<Option<Template>> {
-        self.db.templates().get_by_id_and_org(id, org_id).await
+        let template = self.db.templates().get_by_id_and_org(id, org_id).await?;
+        template.map(|t| self.decrypt(t)).transpose()
     }
 
     /// List all templates accessible within an organization
@@ -41,7 +74,8 @@ impl TemplateService {
         org_id: Uuid,
         params: ListParams,
     ) -> DbResult<ListResult<Template>> {
-        self.db.templates().list_by_org(org_id, params).await
+        let result = self.db.templates().list_by_org(org_id, params).await?;
+        self.decrypt_list(result)
     }
 
     /// List templates by owner with pagination
@@ -51,10 +85,12 @@ impl TemplateService {
         owner_id: Uuid,
         params: ListParams,
     ) -> DbResult<ListResult<Template>> {
-        self.db
+        let result = self
+            .db
             .templates()
             .list_by_owner(owner_type, owner_id, params)
-            .await
+            .await?;
+        self.decrypt_list(result)
     }
 
     /// Count templates by owner
@@ -71,8 +107,22 @@ impl TemplateService {
     }
 
     /// Update a template by ID
-    pub async fn update(&self, id: Uuid, input: UpdateTemplate) -> DbResult<Template> {
-        self.db.templates().update(id, input).await
+    pub async fn update(&self, id: Uuid, mut input: UpdateTemplate) -> DbResult<Template> {
+        if let (Some(enc), Some(content)) = (&self.encryption, input.content.as_mut()) {
+            *content = enc.encrypt(content)?;
+        }
+        let template = self.db.templates().update(id, input).await?;
+        self.decrypt(template)
+    }
+
+    /// Replace a template's labels
+    pub async fn set_labels(
+        &self,
+        id: Uuid,
+        labels: &BTreeMap<String, String>,
+    ) -> DbResult<Template> {
+        let template = self.db.templates().set_labels(id, labels).await?;
+        self.decrypt(template)
     }
 
     /// Delete (soft-delete) a template by ID
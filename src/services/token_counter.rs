@@ -0,0 +1,440 @@
+//! Token counting with each model's own tokenizer.
+//!
+//! With `[features.token_counting]` enabled, prompts are counted with the
+//! tokenizer the model actually uses rather than the usual 4-characters-per-
+//! token estimate:
+//!
+//! - models matching a `tokenizers` rule use that Hugging Face
+//!   `tokenizer.json`;
+//! - OpenAI models use tiktoken (`o200k_base` for GPT-4o and later, o-series
+//!   and GPT-5, `cl100k_base` for GPT-4 and GPT-3.5);
+//! - models served by an Anthropic provider use Anthropic's `count_tokens`
+//!   API, at `/api/v1/tokenize` only;
+//! - anything else is estimated.
+//!
+//! Chat messages add OpenAI's per-message framing (3 tokens per message, 1
+//! per name, 3 to prime the reply) whichever local tokenizer counts them.
+//! Images, audio and files aren't counted.
+//!
+//! The same counts size rate limit and budget reservations, where they
+//! replace `estimated_tokens_per_request` and `estimated_cost_cents`.
+
+use serde_json::Value;
+
+use crate::{
+    AppState,
+    api_types::CreateChatCompletionPayload,
+    config::{ProviderConfig, TokenCountingConfig},
+    providers::{Provider, anthropic},
+    routing::{RoutedProvider, route_model_extended},
+};
+
+/// Request fields holding prompt text.
+const PROMPT_FIELDS: &[&str] = &["messages", "input", "prompt", "instructions", "system"];
+
+/// Keys whose string values are identifiers or media, not prompt text.
+const SKIPPED_KEYS: &[&str] = &[
+    "type",
+    "id",
+    "call_id",
+    "tool_call_id",
+    "status",
+    "role",
+    "image_url",
+    "input_audio",
+    "file",
+    "file_id",
+    "file_data",
+    "detail",
+];
+
+/// Framing tokens per chat message.
+const TOKENS_PER_MESSAGE: u64 = 3;
+/// Extra framing token for a named message.
+const TOKENS_PER_NAME: u64 = 1;
+/// Tokens priming the assistant's reply.
+const REPLY_PRIMING_TOKENS: u64 = 3;
+
+/// Fields holding the requested output cap, by endpoint.
+const MAX_OUTPUT_FIELDS: &[&str] = &["max_completion_tokens", "max_tokens", "max_output_tokens"];
+
+/// A tokenizer chosen for a model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tokenizer {
+    /// tiktoken `o200k_base`
+    O200kBase,
+    /// tiktoken `cl100k_base`
+    Cl100kBase,
+    /// The `tokenizers` rule at this index
+    HuggingFace(usize),
+    /// About 4 characters per token
+    Estimate,
+}
+
+/// The number of tokens in a request's prompt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenCount {
+    pub tokens: u64,
+    /// `o200k_base`, `cl100k_base`, `huggingface`, `anthropic` or `estimate`
+    pub tokenizer: &'static str,
+}
+
+impl TokenCount {
+    /// Whether the count came from a real tokenizer.
+    pub fn is_exact(&self) -> bool {
+        self.tokenizer != "estimate"
+    }
+}
+
+/// Reservation for a data-plane request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LimitEstimate {
+    /// Prompt tokens plus the requested output cap
+    pub tokens: i64,
+    /// Cost of `tokens` at the model's pricing, when it has any
+    pub cost_microcents: Option<i64>,
+}
+
+/// Counts tokens per `[features.token_counting]`.
+pub struct TokenCounter {
+    config: TokenCountingConfig,
+    #[cfg(feature = "token-counting-hf")]
+    hf: Vec<tokenizers::Tokenizer>,
+}
+
+impl TokenCounter {
+    /// Load the configured tokenizers. `None` when counting is disabled.
+    pub fn from_config(config: &TokenCountingConfig) -> Result<Option<Self>, String> {
+        if !config.enabled {
+            return Ok(None);
+        }
+        #[cfg(feature = "token-counting-hf")]
+        let hf = config
+            .tokenizers
+            .iter()
+            .map(|rule| {
+                tokenizers::Tokenizer::from_file(&rule.path)
+                    .map_err(|e| format!("failed to load tokenizer '{}': {e}", rule.path))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Some(Self {
+            config: config.clone(),
+            #[cfg(feature = "token-counting-hf")]
+            hf,
+        }))
+    }
+
+    pub fn use_for_limits(&self) -> bool {
+        self.config.use_for_limits
+    }
+
+    /// The local tokenizer for `model`, as requested or after routing.
+    pub fn tokenizer_for(&self, requested: &str, model: &str) -> Tokenizer {
+        if let Some(i) = self
+            .config
+            .tokenizers
+            .iter()
+            .position(|rule| rule.matches(requested) || rule.matches(model))
+        {
+            return Tokenizer::HuggingFace(i);
+        }
+        openai_tokenizer(model).unwrap_or(Tokenizer::Estimate)
+    }
+
+    /// Count the prompt of a request body with a local tokenizer.
+    pub fn count_local(&self, requested: &str, model: &str, body: &Value) -> TokenCount {
+        let tokenizer = self.tokenizer_for(requested, model);
+        let tokens = match tokenizer {
+            Tokenizer::O200kBase | Tokenizer::Cl100kBase => match tiktoken_count(tokenizer, body) {
+                Some(tokens) => tokens,
+                None => return estimate(body),
+            },
+            Tokenizer::HuggingFace(i) => match self.hf_count(i, body) {
+                Some(tokens) => tokens,
+                None => return estimate(body),
+            },
+            Tokenizer::Estimate => return estimate(body),
+        };
+        TokenCount {
+            tokens,
+            tokenizer: match tokenizer {
+                Tokenizer::O200kBase => "o200k_base",
+                Tokenizer::Cl100kBase => "cl100k_base",
+                _ => "huggingface",
+            },
+        }
+    }
+
+    #[cfg(feature = "token-counting-hf")]
+    fn hf_count(&self, index: usize, body: &Value) -> Option<u64> {
+        let tokenizer = self.hf.get(index)?;
+        Some(count_body(body, |text| {
+            tokenizer
+                .encode(text, false)
+                .map(|encoding| encoding.len())
+                .unwrap_or_else(|_| text.chars().count().div_ceil(4))
+        }))
+    }
+
+    #[cfg(not(feature = "token-counting-hf"))]
+    fn hf_count(&self, _index: usize, _body: &Value) -> Option<u64> {
+        None
+    }
+
+    /// Count a request for `provider_config`/`model`, asking Anthropic's
+    /// counting API for Anthropic providers when `anthropic_api` is set.
+    ///
+    /// Falls back to the local count if the API call fails.
+    pub async fn count(
+        &self,
+        state: &AppState,
+        provider_name: &str,
+        provider_config: &ProviderConfig,
+        requested: &str,
+        model: &str,
+        body: &Value,
+    ) -> TokenCount {
+        if self.config.anthropic_api
+            && let ProviderConfig::Anthropic(config) = provider_config
+            && let Ok(mut payload) =
+                serde_json::from_value::<CreateChatCompletionPayload>(body.clone())
+        {
+            payload.model = Some(model.to_string());
+            let provider = anthropic::AnthropicProvider::from_config_with_registry_and_image_config(
+                config,
+                provider_name,
+                &state.circuit_breakers,
+                state.config.features.image_fetching.to_runtime_config(),
+            );
+            match provider.count_tokens(&state.http_client, payload).await {
+                Ok(tokens) => {
+                    return TokenCount {
+                        tokens,
+                        tokenizer: "anthropic",
+                    };
+                }
+                Err(e) => tracing::warn!(
+                    provider = provider_name,
+                    error = %e,
+                    "Token counting API failed, counting locally"
+                ),
+            }
+        }
+        self.count_local(requested, model, body)
+    }
+
+    /// Size a data-plane request's rate limit and budget reservation.
+    ///
+    /// `None` when the body has no `model`. Requests routed to a dynamic
+    /// provider are counted but not priced.
+    pub fn estimate_limits(&self, state: &AppState, body: &Value) -> Option<LimitEstimate> {
+        let requested = body.get("model")?.as_str()?;
+        let (provider, model) = match route_model_extended(Some(requested), &state.config.providers)
+        {
+            Ok(RoutedProvider::Static(route)) => (Some(route.provider_name), route.model),
+            Ok(RoutedProvider::Dynamic(route)) => (None, route.model),
+            Err(_) => (None, requested.to_string()),
+        };
+        let prompt = self.count_local(requested, &model, body).tokens as i64;
+        let output = max_output_tokens(body);
+        let cost_microcents = provider.and_then(|provider| {
+            state
+                .pricing
+                .calculate_cost(provider, &model, prompt, output)
+                .map(|(cost, _)| cost)
+        });
+        Some(LimitEstimate {
+            tokens: prompt.saturating_add(output),
+            cost_microcents,
+        })
+    }
+}
+
+/// The tiktoken encoding for an OpenAI model, by name.
+fn openai_tokenizer(model: &str) -> Option<Tokenizer> {
+    const O200K: &[&str] = &[
+        "gpt-4o",
+        "gpt-4.1",
+        "gpt-4.5",
+        "gpt-5",
+        "gpt-oss",
+        "chatgpt-4o",
+        "o1",
+        "o3",
+        "o4",
+        "codex-",
+    ];
+    const CL100K: &[&str] = &[
+        "gpt-4",
+        "gpt-3.5",
+        "gpt-35",
+        "text-embedding-",
+        "davinci-002",
+        "babbage-002",
+    ];
+    let model = model.rsplit('/').next().unwrap_or(model);
+    if O200K.iter().any(|prefix| model.starts_with(prefix)) {
+        Some(Tokenizer::O200kBase)
+    } else if CL100K.iter().any(|prefix| model.starts_with(prefix)) {
+        Some(Tokenizer::Cl100kBase)
+    } else {
+        None
+    }
+}
+
+#[cfg(feature = "token-counting")]
+fn tiktoken_count(tokenizer: Tokenizer, body: &Value) -> Option<u64> {
+    use std::sync::OnceLock;
+
+    static O200K: OnceLock<Option<tiktoken_rs::CoreBPE>> = OnceLock::new();
+    static CL100K: OnceLock<Option<tiktoken_rs::CoreBPE>> = OnceLock::new();
+    let bpe = match tokenizer {
+        Tokenizer::O200kBase => O200K.get_or_init(|| tiktoken_rs::o200k_base().ok()),
+        Tokenizer::Cl100kBase => CL100K.get_or_init(|| tiktoken_rs::cl100k_base().ok()),
+        _ => return None,
+    };
+    let bpe = bpe.as_ref()?;
+    Some(count_body(body, |text| bpe.encode_ordinary(text).len()))
+}
+
+#[cfg(not(feature = "token-counting"))]
+fn tiktoken_count(_tokenizer: Tokenizer, _body: &Value) -> Option<u64> {
+    None
+}
+
+/// Estimate at about 4 characters per token.
+pub fn estimate(body: &Value) -> TokenCount {
+    TokenCount {
+        tokens: count_body(body, |text| text.chars().count().div_ceil(4)),
+        tokenizer: "estimate",
+    }
+}
+
+/// Requested output cap, 0 when the request sets none.
+pub fn max_output_tokens(body: &Value) -> i64 {
+    MAX_OUTPUT_FIELDS
+        .iter()
+        .find_map(|field| body.get(field).and_then(Value::as_i64))
+        .unwrap_or(0)
+        .max(0)
+}
+
+/// Count the prompt fields and tool definitions of a request body.
+fn count_body(body: &Value, count: impl Fn(&str) -> usize) -> u64 {
+    let mut tokens = 0;
+    for field in PROMPT_FIELDS {
+        match body.get(field) {
+            Some(Value::Array(messages)) if *field == "messages" => {
+                for message in messages {
+                    tokens += TOKENS_PER_MESSAGE + count_value(message, &count);
+                    if let Some(role) = message.get("role").and_then(Value::as_str) {
+                        tokens += count(role) as u64;
+                    }
+                    if message.get("name").is_some_and(Value::is_string) {
+                        tokens += TOKENS_PER_NAME;
+                    }
+                }
+                if !messages.is_empty() {
+                    tokens += REPLY_PRIMING_TOKENS;
+                }
+            }
+            Some(value) => tokens += count_value(value, &count),
+            None => {}
+        }
+    }
+    if let Some(tools) = body.get("tools").filter(|t| !t.is_null()) {
+        tokens += count(&tools.to_string()) as u64;
+    }
+    tokens
+}
+
+/// Count the text under `value`, skipping identifiers and media. Arrays of
+/// integers are pre-tokenized input and count one token each.
+fn count_value(value: &Value, count: &impl Fn(&str) -> usize) -> u64 {
+    match value {
+        Value::String(text) => count(text) as u64,
+        Value::Number(_) => 1,
+        Value::Array(items) => items.iter().map(|item| count_value(item, count)).sum(),
+        Value::Object(map) => map
+            .iter()
+            .filter(|(key, _)| !SKIPPED_KEYS.contains(&key.as_str()))
+            .map(|(_, value)| count_value(value, count))
+            .sum(),
+        Value::Bool(_) | Value::Null => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn counter() -> TokenCounter {
+        TokenCounter::from_config(&TokenCountingConfig {
+            enabled: true,
+            ..Default::default()
+        })
+        .unwrap()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_openai_tokenizer_by_model() {
+        assert_eq!(openai_tokenizer("gpt-4o-mini"), Some(Tokenizer::O200kBase));
+        assert_eq!(
+            openai_tokenizer("openai/o3-mini"),
+            Some(Tokenizer::O200kBase)
+        );
+        assert_eq!(openai_tokenizer("gpt-4-turbo"), Some(Tokenizer::Cl100kBase));
+        assert_eq!(
+            openai_tokenizer("text-embedding-3-small"),
+            Some(Tokenizer::Cl100kBase)
+        );
+        assert_eq!(openai_tokenizer("claude-sonnet-4-5"), None);
+    }
+
+    #[test]
+    fn test_estimate_counts_message_framing() {
+        let body = json!({
+            "model": "claude-sonnet-4-5",
+            "messages": [
+                {"role": "system", "content": "Be brief"},
+                {"role": "user", "name": "ada", "content": [
+                    {"type": "text", "text": "Hello there"},
+                    {"type": "image_url", "image_url": {"url": "data:image/png;base64,AAAA"}}
+                ]}
+            ]
+        });
+        let count = counter().count_local("claude-sonnet-4-5", "claude-sonnet-4-5", &body);
+        assert_eq!(count.tokenizer, "estimate");
+        assert!(!count.is_exact());
+        // "Be brief" 2 + "system" 2, "Hello there" 3 + "ada" 1 + "user" 1,
+        // 2 messages * 3 + 1 name + 3 priming
+        assert_eq!(count.tokens, 2 + 2 + 3 + 1 + 1 + 6 + 1 + 3);
+    }
+
+    #[test]
+    fn test_pretokenized_input() {
+        let body = json!({"model": "text-embedding-3-small", "input": [[1, 2, 3], [4]]});
+        assert_eq!(count_body(&body, |_| 100), 4);
+    }
+
+    #[test]
+    fn test_max_output_tokens() {
+        assert_eq!(max_output_tokens(&json!({"max_tokens": 256})), 256);
+        assert_eq!(max_output_tokens(&json!({"max_output_tokens": 64})), 64);
+        assert_eq!(max_output_tokens(&json!({})), 0);
+    }
+
+    #[cfg(feature = "token-counting")]
+    #[test]
+    fn test_tiktoken_counts() {
+        let body =
+            json!({"model": "gpt-4o", "messages": [{"role": "user", "content": "Hello world"}]});
+        let count = counter().count_local("gpt-4o", "gpt-4o", &body);
+        assert_eq!(count.tokenizer, "o200k_base");
+        // "Hello world" 2 + "user" 1 + framing 3 + priming 3
+        assert_eq!(count.tokens, 9);
+    }
+}
@@ -14,9 +14,10 @@ use crate::{
     },
     models::{
         CostForecast, DailyModelSpend, DailyOrgSpend, DailyPricingSourceSpend, DailyProjectSpend,
-        DailyProviderSpend, DailySpend, DailyTeamSpend, DailyUserSpend, ModelSpend, OrgSpend,
-        PricingSourceSpend, ProjectSpend, ProviderSpend, RefererSpend, TeamSpend, UsageLogEntry,
-        UsageLogRecord, UsageSummary, UserSpend,
+        DailyProviderAttempts, DailyProviderSpend, DailyReliability, DailySpend, DailyTagSpend,
+        DailyTeamSpend, DailyUserSpend, ModelSpend, OrgSpend, PricingSourceSpend, ProjectSpend,
+        ProviderSpend, RefererSpend, TagSpend, TeamSpend, UsageLogEntry, UsageLogRecord,
+        UsageSummary, UserSpend,
     },
 };
 
@@ -819,6 +820,51 @@ impl UsageService {
         self.db.usage().get_provider_usage_global(range).await
     }
 
+    pub async fn get_daily_provider_attempts(
+        &self,
+        range: DateRange,
+    ) -> DbResult<Vec<DailyProviderAttempts>> {
+        self.db.usage().get_daily_provider_attempts(range).await
+    }
+
+    pub async fn get_by_tag(
+        &self,
+        key: &str,
+        range: DateRange,
+        org_id: Option<Uuid>,
+        project_id: Option<Uuid>,
+    ) -> DbResult<Vec<TagSpend>> {
+        self.db
+            .usage()
+            .get_tag_usage(key, range, org_id, project_id)
+            .await
+    }
+
+    pub async fn get_by_date_tag(
+        &self,
+        key: &str,
+        range: DateRange,
+        org_id: Option<Uuid>,
+        project_id: Option<Uuid>,
+    ) -> DbResult<Vec<DailyTagSpend>> {
+        self.db
+            .usage()
+            .get_daily_tag_usage(key, range, org_id, project_id)
+            .await
+    }
+
+    pub async fn get_daily_reliability(
+        &self,
+        range: DateRange,
+        org_id: Option<Uuid>,
+        project_id: Option<Uuid>,
+    ) -> DbResult<Vec<DailyReliability>> {
+        self.db
+            .usage()
+            .get_daily_reliability(range, org_id, project_id)
+            .await
+    }
+
     pub async fn get_by_pricing_source_global(
         &self,
         range: DateRange,
@@ -1460,6 +1506,20 @@ mod tests {
             tool_results_count: None,
             tool_runtime_seconds: None,
             tool_exit_code: None,
+            jwt_subject: None,
+            error_code: None,
+            structured_output_repairs: None,
+            context_original_tokens: None,
+            context_compressed_tokens: None,
+            degraded_from_model: None,
+            degradation_reason: None,
+            ttft_ms: None,
+            inter_token_latency_ms: None,
+            output_tokens_per_second: None,
+            idempotency_key: None,
+            provider_attempts: None,
+            suspected_duplicate_attempts: None,
+            tags: None,
         }
     }
 
@@ -1506,6 +1566,20 @@ mod tests {
             tool_results_count: None,
             tool_runtime_seconds: None,
             tool_exit_code: None,
+            jwt_subject: None,
+            error_code: None,
+            structured_output_repairs: None,
+            context_original_tokens: None,
+            context_compressed_tokens: None,
+            degraded_from_model: None,
+            degradation_reason: None,
+            ttft_ms: None,
+            inter_token_latency_ms: None,
+            output_tokens_per_second: None,
+            idempotency_key: None,
+            provider_attempts: None,
+            suspected_duplicate_attempts: None,
+            tags: None,
         }
     }
 
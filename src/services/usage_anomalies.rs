@@ -0,0 +1,513 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{
+    cache::{Cache, CacheKeys},
+    config::AnomalyDetectionConfig,
+    db::{
+        DbPool, DbResult,
+        repos::{ListParams, ListResult},
+    },
+    models::{
+        AnomalyKind, CreateUsageAnomaly, HourlySpend, UsageAnomaly, UsageAnomalyFilter, UsageMix,
+    },
+};
+
+/// Service layer for usage anomaly findings
+#[derive(Clone)]
+pub struct UsageAnomalyService {
+    db: Arc<DbPool>,
+}
+
+impl UsageAnomalyService {
+    pub fn new(db: Arc<DbPool>) -> Self {
+        Self { db }
+    }
+
+    /// Get an anomaly by ID
+    pub async fn get_by_id(&self, id: Uuid) -> DbResult<Option<UsageAnomaly>> {
+        self.db.usage_anomalies().get_by_id(id).await
+    }
+
+    /// List anomalies, newest first
+    pub async fn list(
+        &self,
+        filter: &UsageAnomalyFilter,
+        params: ListParams,
+    ) -> DbResult<ListResult<UsageAnomaly>> {
+        self.db.usage_anomalies().list(filter, params).await
+    }
+
+    /// Acknowledge an anomaly, lifting any temporary rate limit it applied
+    pub async fn acknowledge(
+        &self,
+        id: Uuid,
+        acknowledged_by: Option<Uuid>,
+        cache: Option<&Arc<dyn Cache>>,
+    ) -> DbResult<Option<UsageAnomaly>> {
+        let anomaly = self
+            .db
+            .usage_anomalies()
+            .acknowledge(id, acknowledged_by)
+            .await?;
+
+        if let Some(anomaly) = &anomaly
+            && let (Some(api_key_id), Some(until), Some(cache)) =
+                (anomaly.api_key_id, anomaly.rate_limited_until, cache)
+            && until > Utc::now()
+            && let Err(e) = cache
+                .delete(&CacheKeys::anomaly_rate_limit(api_key_id))
+                .await
+        {
+            tracing::warn!(
+                error = %e,
+                %api_key_id,
+                "Failed to lift anomaly rate limit"
+            );
+        }
+
+        Ok(anomaly)
+    }
+}
+
+/// Usage compared by one detection pass.
+#[derive(Debug, Default)]
+pub struct UsageSnapshot {
+    pub baseline_spend: Vec<HourlySpend>,
+    pub window_spend: Vec<HourlySpend>,
+    pub baseline_mix: Vec<UsageMix>,
+    pub window_mix: Vec<UsageMix>,
+}
+
+/// Who an anomaly is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Subject {
+    ApiKey(Uuid),
+    Org(Uuid),
+}
+
+impl Subject {
+    /// The API key and organization subjects a usage row counts towards.
+    fn of(api_key_id: Option<Uuid>, org_id: Option<Uuid>) -> impl Iterator<Item = Subject> {
+        api_key_id
+            .map(Subject::ApiKey)
+            .into_iter()
+            .chain(org_id.map(Subject::Org))
+    }
+
+    fn fingerprint(&self, kind: AnomalyKind, detail: Option<&str>) -> String {
+        let subject = match self {
+            Subject::ApiKey(id) => format!("api_key:{id}"),
+            Subject::Org(id) => format!("org:{id}"),
+        };
+        match detail {
+            Some(detail) => format!("{}:{subject}:{detail}", kind.as_str()),
+            None => format!("{}:{subject}", kind.as_str()),
+        }
+    }
+
+    fn describe(&self) -> &'static str {
+        match self {
+            Subject::ApiKey(_) => "API key",
+            Subject::Org(_) => "Organization",
+        }
+    }
+}
+
+/// Requests and referers of one subject over a period.
+#[derive(Debug, Default)]
+struct Mix {
+    requests: i64,
+    by_model: HashMap<String, i64>,
+    by_referer: HashMap<String, i64>,
+}
+
+impl Mix {
+    fn collect(rows: &[UsageMix]) -> HashMap<Subject, Mix> {
+        let mut mixes: HashMap<Subject, Mix> = HashMap::new();
+        for row in rows {
+            for subject in Subject::of(row.api_key_id, row.org_id) {
+                let mix = mixes.entry(subject).or_default();
+                mix.requests += row.request_count;
+                *mix.by_model.entry(row.model.clone()).or_default() += row.request_count;
+                if let Some(referer) = &row.referer {
+                    *mix.by_referer.entry(referer.clone()).or_default() += row.request_count;
+                }
+            }
+        }
+        mixes
+    }
+
+    fn share(&self, model: &str) -> f64 {
+        if self.requests == 0 {
+            return 0.0;
+        }
+        self.by_model.get(model).copied().unwrap_or(0) as f64 / self.requests as f64
+    }
+}
+
+fn dollars(microcents: f64) -> f64 {
+    microcents / 1_000_000.0
+}
+
+/// Find anomalies in `usage`, where the window is `[window_start, window_end)`
+/// and the baseline is the `baseline_days` before it.
+///
+/// Each check runs for every API key and every organization (all of its
+/// keys and session usage together) that had usage in the baseline; the
+/// returned findings have no rate limit applied yet.
+pub fn detect(
+    config: &AnomalyDetectionConfig,
+    usage: &UsageSnapshot,
+    window_start: DateTime<Utc>,
+    window_end: DateTime<Utc>,
+) -> Vec<CreateUsageAnomaly> {
+    let mut key_orgs: HashMap<Uuid, Option<Uuid>> = HashMap::new();
+    for (api_key_id, org_id) in usage
+        .baseline_spend
+        .iter()
+        .chain(&usage.window_spend)
+        .map(|row| (row.api_key_id, row.org_id))
+        .chain(
+            usage
+                .baseline_mix
+                .iter()
+                .chain(&usage.window_mix)
+                .map(|row| (row.api_key_id, row.org_id)),
+        )
+    {
+        if let Some(api_key_id) = api_key_id {
+            key_orgs.entry(api_key_id).or_insert(org_id);
+        }
+    }
+
+    let finding = |subject: Subject,
+                   kind: AnomalyKind,
+                   detail: Option<String>,
+                   summary: String,
+                   observed: f64,
+                   expected: f64,
+                   score: f64| {
+        let (api_key_id, org_id) = match subject {
+            Subject::ApiKey(id) => (Some(id), key_orgs.get(&id).copied().flatten()),
+            Subject::Org(id) => (None, Some(id)),
+        };
+        CreateUsageAnomaly {
+            kind,
+            org_id,
+            api_key_id,
+            fingerprint: subject.fingerprint(kind, detail.as_deref()),
+            detail,
+            summary,
+            observed,
+            expected,
+            score,
+            window_start,
+            window_end,
+            rate_limited_until: None,
+        }
+    };
+
+    let mut anomalies = Vec::new();
+
+    // Spend spikes: window spend against the mean and spread of hourly
+    // baseline spend, counting hours without usage as zero
+    let mut baseline_hours: HashMap<Subject, HashMap<DateTime<Utc>, i64>> = HashMap::new();
+    for row in &usage.baseline_spend {
+        for subject in Subject::of(row.api_key_id, row.org_id) {
+            *baseline_hours
+                .entry(subject)
+                .or_default()
+                .entry(row.hour)
+                .or_default() += row.total_cost_microcents;
+        }
+    }
+    let mut window_spend: HashMap<Subject, i64> = HashMap::new();
+    for row in &usage.window_spend {
+        for subject in Subject::of(row.api_key_id, row.org_id) {
+            *window_spend.entry(subject).or_default() += row.total_cost_microcents;
+        }
+    }
+
+    let hours = f64::from(config.baseline_days) * 24.0;
+    let window_hours = f64::from(config.window_hours);
+    for (subject, spend) in &window_spend {
+        if *spend < config.min_spend_microcents {
+            continue;
+        }
+        let Some(baseline) = baseline_hours.get(subject) else {
+            continue;
+        };
+        let total: f64 = baseline.values().map(|&v| v as f64).sum();
+        if total <= 0.0 {
+            continue;
+        }
+        let mean = total / hours;
+        let variance =
+            (baseline.values().map(|&v| (v as f64).powi(2)).sum::<f64>() / hours) - mean.powi(2);
+        let expected = mean * window_hours;
+        // A very steady baseline has almost no spread; don't let that turn
+        // small fluctuations into spikes
+        let spread = (variance.max(0.0).sqrt() * window_hours.sqrt())
+            .max(expected * 0.1)
+            .max(1.0);
+        let observed = *spend as f64;
+        let score = (observed - expected) / spread;
+        if score >= config.spend_spike_zscore {
+            anomalies.push(finding(
+                *subject,
+                AnomalyKind::SpendSpike,
+                None,
+                format!(
+                    "{} spent ${:.2} in the last {}h, against ${:.2} expected from the {}-day \
+                     baseline (z = {:.1})",
+                    subject.describe(),
+                    dollars(observed),
+                    config.window_hours,
+                    dollars(expected),
+                    config.baseline_days,
+                    score
+                ),
+                observed,
+                expected,
+                score,
+            ));
+        }
+    }
+
+    let baseline_mix = Mix::collect(&usage.baseline_mix);
+    let window_mix = Mix::collect(&usage.window_mix);
+    for (subject, window) in &window_mix {
+        let Some(baseline) = baseline_mix.get(subject).filter(|b| b.requests > 0) else {
+            continue;
+        };
+
+        // Model mix: total variation distance between request shares
+        if window.requests >= config.min_requests {
+            let models: HashSet<&String> = window
+                .by_model
+                .keys()
+                .chain(baseline.by_model.keys())
+                .collect();
+            let distance = models
+                .iter()
+                .map(|m| (window.share(m) - baseline.share(m)).abs())
+                .sum::<f64>()
+                / 2.0;
+            // Name the model whose share grew the most
+            let grown = window
+                .by_model
+                .keys()
+                .map(|m| (m, window.share(m) - baseline.share(m)))
+                .max_by(|a, b| a.1.total_cmp(&b.1).then_with(|| b.0.cmp(a.0)));
+            if distance >= config.model_mix_threshold
+                && let Some((model, _)) = grown
+            {
+                let observed = window.share(model);
+                let expected = baseline.share(model);
+                anomalies.push(finding(
+                    *subject,
+                    AnomalyKind::ModelMix,
+                    Some(model.clone()),
+                    format!(
+                        "{} model mix shifted by {:.0}%: {} went from {:.0}% to {:.0}% of requests",
+                        subject.describe(),
+                        distance * 100.0,
+                        model,
+                        expected * 100.0,
+                        observed * 100.0
+                    ),
+                    observed,
+                    expected,
+                    distance,
+                ));
+            }
+        }
+
+        // New referers
+        for (referer, &requests) in &window.by_referer {
+            if requests >= config.new_referer_min_requests
+                && !baseline.by_referer.contains_key(referer)
+            {
+                anomalies.push(finding(
+                    *subject,
+                    AnomalyKind::NewReferer,
+                    Some(referer.clone()),
+                    format!(
+                        "{} received {} requests from new referer {}",
+                        subject.describe(),
+                        requests,
+                        referer
+                    ),
+                    requests as f64,
+                    0.0,
+                    requests as f64,
+                ));
+            }
+        }
+    }
+
+    anomalies
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+
+    fn config() -> AnomalyDetectionConfig {
+        AnomalyDetectionConfig {
+            enabled: true,
+            baseline_days: 1,
+            min_spend_microcents: 100,
+            min_requests: 10,
+            new_referer_min_requests: 5,
+            ..Default::default()
+        }
+    }
+
+    fn spend(api_key_id: Uuid, org_id: Uuid, hour: DateTime<Utc>, cost: i64) -> HourlySpend {
+        HourlySpend {
+            api_key_id: Some(api_key_id),
+            org_id: Some(org_id),
+            hour,
+            total_cost_microcents: cost,
+            request_count: 1,
+        }
+    }
+
+    fn mix(
+        api_key_id: Uuid,
+        org_id: Uuid,
+        model: &str,
+        referer: Option<&str>,
+        requests: i64,
+    ) -> UsageMix {
+        UsageMix {
+            api_key_id: Some(api_key_id),
+            org_id: Some(org_id),
+            model: model.to_string(),
+            referer: referer.map(String::from),
+            total_cost_microcents: requests * 10,
+            request_count: requests,
+        }
+    }
+
+    #[test]
+    fn test_detect_spend_spike() {
+        let (key, org) = (Uuid::new_v4(), Uuid::new_v4());
+        let now = Utc::now();
+        let baseline_spend = (1..=24)
+            .map(|h| spend(key, org, now - Duration::hours(h + 1), 1_000))
+            .collect();
+        let usage = UsageSnapshot {
+            baseline_spend,
+            window_spend: vec![spend(key, org, now, 50_000)],
+            ..Default::default()
+        };
+
+        let anomalies = detect(&config(), &usage, now - Duration::hours(1), now);
+        assert_eq!(anomalies.len(), 2);
+        let key_spike = anomalies
+            .iter()
+            .find(|a| a.api_key_id == Some(key))
+            .expect("key finding");
+        assert_eq!(key_spike.kind, AnomalyKind::SpendSpike);
+        assert_eq!(key_spike.org_id, Some(org));
+        assert_eq!(key_spike.observed, 50_000.0);
+        assert_eq!(key_spike.expected, 1_000.0);
+        assert!(key_spike.score >= 4.0);
+        assert_eq!(key_spike.fingerprint, format!("spend_spike:api_key:{key}"));
+        let org_spike = anomalies
+            .iter()
+            .find(|a| a.api_key_id.is_none())
+            .expect("org finding");
+        assert_eq!(org_spike.org_id, Some(org));
+        assert_eq!(org_spike.fingerprint, format!("spend_spike:org:{org}"));
+
+        // Spend in line with the baseline isn't a spike
+        let usage = UsageSnapshot {
+            window_spend: vec![spend(key, org, now, 1_200)],
+            ..usage
+        };
+        assert!(detect(&config(), &usage, now - Duration::hours(1), now).is_empty());
+    }
+
+    #[test]
+    fn test_detect_skips_subjects_without_baseline() {
+        let (key, org) = (Uuid::new_v4(), Uuid::new_v4());
+        let now = Utc::now();
+        let usage = UsageSnapshot {
+            window_spend: vec![spend(key, org, now, 1_000_000)],
+            window_mix: vec![mix(key, org, "gpt-4o", Some("https://new.example.com"), 50)],
+            ..Default::default()
+        };
+        assert!(detect(&config(), &usage, now - Duration::hours(1), now).is_empty());
+
+        // Spend below the minimum is ignored however unusual
+        let usage = UsageSnapshot {
+            baseline_spend: vec![spend(key, org, now - Duration::hours(5), 1)],
+            window_spend: vec![spend(key, org, now, 99)],
+            ..Default::default()
+        };
+        assert!(detect(&config(), &usage, now - Duration::hours(1), now).is_empty());
+    }
+
+    #[test]
+    fn test_detect_model_mix_and_new_referer() {
+        let (key, org) = (Uuid::new_v4(), Uuid::new_v4());
+        let now = Utc::now();
+        let app = Some("https://app.example.com");
+        let usage = UsageSnapshot {
+            baseline_mix: vec![
+                mix(key, org, "gpt-4o-mini", app, 90),
+                mix(key, org, "gpt-4o", app, 10),
+            ],
+            window_mix: vec![
+                mix(key, org, "gpt-4o-mini", app, 2),
+                mix(key, org, "o1", app, 14),
+                mix(key, org, "o1", Some("https://scraper.example.net"), 4),
+            ],
+            ..Default::default()
+        };
+
+        let anomalies = detect(&config(), &usage, now - Duration::hours(1), now);
+        let key_mix: Vec<_> = anomalies
+            .iter()
+            .filter(|a| a.api_key_id == Some(key))
+            .collect();
+        // The new referer has too few requests to report
+        assert_eq!(key_mix.len(), 1);
+        assert_eq!(key_mix[0].kind, AnomalyKind::ModelMix);
+        assert_eq!(key_mix[0].detail.as_deref(), Some("o1"));
+        assert_eq!(key_mix[0].observed, 0.9);
+        assert_eq!(key_mix[0].expected, 0.0);
+        assert!((key_mix[0].score - 0.9).abs() < 1e-9);
+
+        let lenient = AnomalyDetectionConfig {
+            new_referer_min_requests: 4,
+            model_mix_threshold: 1.0,
+            ..config()
+        };
+        let anomalies = detect(&lenient, &usage, now - Duration::hours(1), now);
+        assert_eq!(anomalies.len(), 2);
+        assert!(anomalies.iter().all(|a| a.kind == AnomalyKind::NewReferer));
+        assert!(
+            anomalies
+                .iter()
+                .all(|a| a.detail.as_deref() == Some("https://scraper.example.net"))
+        );
+        assert!(
+            anomalies
+                .iter()
+                .any(|a| a.fingerprint
+                    == format!("new_referer:org:{org}:https://scraper.example.net"))
+        );
+    }
+}
@@ -0,0 +1,446 @@
+//! Imports of provider-reported usage for reconciliation.
+//!
+//! Usage is pulled from a provider usage API configured under
+//! `[features.usage_reconciliation]`, or uploaded as a CSV export (OpenAI,
+//! Anthropic or Azure Cost Management). Each import replaces earlier imported
+//! usage for its provider and days, and the variance report compares the
+//! stored usage with what the gateway recorded.
+
+use std::sync::Arc;
+
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::usage_reconciliation::{self, ProviderReconciliation};
+use crate::{
+    config::{ProviderUsageApi, UsageReconciliationConfig},
+    db::{DateRange, DbError, DbPool, DbResult},
+    models::{
+        CreateProviderUsageImport, ImportedUsage, ProviderUsageImport, UsageImportMethod,
+        UsageImportSource,
+    },
+};
+
+/// Errors that can occur while importing provider usage.
+#[derive(Debug, Error)]
+pub enum UsageImportError {
+    #[error("Database error: {0}")]
+    Database(#[from] DbError),
+
+    #[error("No usage API is configured for provider '{0}'")]
+    SourceNotConfigured(String),
+
+    #[error("Failed to fetch provider usage: {0}")]
+    Fetch(String),
+
+    #[error("Invalid usage export: {0}")]
+    Invalid(String),
+}
+
+/// Service layer for provider usage imports and the variance report.
+#[derive(Clone)]
+pub struct ProviderUsageImportService {
+    db: Arc<DbPool>,
+}
+
+impl ProviderUsageImportService {
+    pub fn new(db: Arc<DbPool>) -> Self {
+        Self { db }
+    }
+
+    /// Pull usage for `range` from the provider's configured usage API.
+    pub async fn import_from_api(
+        &self,
+        http_client: &reqwest::Client,
+        config: &UsageReconciliationConfig,
+        provider: &str,
+        range: DateRange,
+    ) -> Result<ProviderUsageImport, UsageImportError> {
+        let source = config
+            .sources
+            .iter()
+            .find(|s| s.provider == provider)
+            .ok_or_else(|| UsageImportError::SourceNotConfigured(provider.to_string()))?;
+        let usage = usage_reconciliation::fetch_reported_usage(
+            http_client,
+            source,
+            &range,
+            config.timeout_secs,
+        )
+        .await
+        .map_err(UsageImportError::Fetch)?;
+
+        let import = CreateProviderUsageImport {
+            provider: provider.to_string(),
+            source: source.api.into(),
+            method: UsageImportMethod::Api,
+            start_date: range.start,
+            end_date: range.end,
+        };
+        Ok(self
+            .db
+            .provider_usage_imports()
+            .record_import(import, &usage)
+            .await?)
+    }
+
+    /// Import an uploaded CSV export. The import covers the first through the
+    /// last day in the file.
+    pub async fn import_csv(
+        &self,
+        provider: &str,
+        source: UsageImportSource,
+        body: &str,
+    ) -> Result<ProviderUsageImport, UsageImportError> {
+        let usage = parse_usage_csv(body).map_err(UsageImportError::Invalid)?;
+        let (Some(start_date), Some(end_date)) = (
+            usage.iter().map(|u| u.date).min(),
+            usage.iter().map(|u| u.date).max(),
+        ) else {
+            return Err(UsageImportError::Invalid(
+                "the file has no usage rows".to_string(),
+            ));
+        };
+
+        let import = CreateProviderUsageImport {
+            provider: provider.to_string(),
+            source,
+            method: UsageImportMethod::Csv,
+            start_date,
+            end_date,
+        };
+        Ok(self
+            .db
+            .provider_usage_imports()
+            .record_import(import, &usage)
+            .await?)
+    }
+
+    /// List imports, newest first
+    pub async fn list(&self, provider: Option<&str>) -> DbResult<Vec<ProviderUsageImport>> {
+        self.db
+            .provider_usage_imports()
+            .list_imports(provider)
+            .await
+    }
+
+    /// Delete an import and its usage. Returns `false` if it doesn't exist.
+    pub async fn delete(&self, id: Uuid) -> DbResult<bool> {
+        self.db.provider_usage_imports().delete_import(id).await
+    }
+
+    /// Compare imported usage with gateway usage per provider, day and model
+    pub async fn report(
+        &self,
+        range: DateRange,
+        provider: Option<&str>,
+    ) -> DbResult<Vec<ProviderReconciliation>> {
+        let mut gateway = self
+            .db
+            .usage()
+            .get_daily_provider_attempts(range.clone())
+            .await?;
+        let mut imports = self
+            .db
+            .provider_usage_imports()
+            .list_imports(provider)
+            .await?;
+        let usage = self
+            .db
+            .provider_usage_imports()
+            .list_usage(range.clone(), provider)
+            .await?;
+
+        if let Some(provider) = provider {
+            gateway.retain(|row| row.provider == provider);
+        }
+        imports.retain(|i| i.start_date <= range.end && i.end_date >= range.start);
+        Ok(usage_reconciliation::reconcile_imported(
+            gateway, imports, usage,
+        ))
+    }
+}
+
+impl From<ProviderUsageApi> for UsageImportSource {
+    fn from(api: ProviderUsageApi) -> Self {
+        match api {
+            ProviderUsageApi::OpenAi => Self::OpenAi,
+            ProviderUsageApi::Anthropic => Self::Anthropic,
+        }
+    }
+}
+
+/// Parse a usage or cost export into daily per-model usage.
+///
+/// Columns are matched by header name, ignoring case, spaces and
+/// underscores, so exports from each provider's console and the usage API
+/// field names both work:
+///
+/// - date: `date`, `usage_date`, `usage_date_utc`, `start_time`,
+///   `start_time_iso`, `starting_at`
+/// - model: `model`, `model_version`, `model_name`
+/// - tokens: `input_tokens`/`prompt_tokens` and
+///   `output_tokens`/`completion_tokens`, or a `token_type` column (output
+///   if it mentions "output") with a `usage`, `tokens` or `quantity` count
+/// - requests: `num_model_requests`, `requests`, `request_count`
+/// - cost in dollars: `cost`, `cost_usd`, `amount`,
+///   `cost_in_billing_currency`, `pretax_cost`
+///
+/// Azure Cost Management exports have no model column. Their `meter_name`
+/// (e.g. `gpt-4o-0806-Inp-glbl 1K Tokens`) gives the model and whether the
+/// `quantity` is input or output, scaled by `unit_of_measure` (`1K`, `1M`).
+#[cfg(feature = "csv-export")]
+pub fn parse_usage_csv(body: &str) -> Result<Vec<ImportedUsage>, String> {
+    use std::collections::BTreeMap;
+
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(body.as_bytes());
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(normalize_header)
+        .collect();
+    let column = |names: &[&str]| {
+        names
+            .iter()
+            .find_map(|n| headers.iter().position(|h| h == n))
+    };
+
+    let date_col = column(&[
+        "date",
+        "usagedate",
+        "usagedateutc",
+        "starttime",
+        "starttimeiso",
+        "startingat",
+    ])
+    .ok_or("missing a date column")?;
+    let model_col = column(&["model", "modelversion", "modelname"]);
+    let meter_col = column(&["metername", "meter"]);
+    if model_col.is_none() && meter_col.is_none() {
+        return Err("missing a model or meter_name column".to_string());
+    }
+    let input_col = column(&["inputtokens", "prompttokens"]);
+    let output_col = column(&["outputtokens", "completiontokens"]);
+    let token_type_col = column(&["tokentype", "usagetype"]);
+    let quantity_col = column(&["usage", "tokens", "quantity"]);
+    let unit_col = column(&["unitofmeasure", "unit"]);
+    let requests_col = column(&["nummodelrequests", "requests", "requestcount"]);
+    let cost_col = column(&[
+        "cost",
+        "costusd",
+        "amount",
+        "costinbillingcurrency",
+        "pretaxcost",
+    ]);
+
+    let mut usage: BTreeMap<(chrono::NaiveDate, String), ImportedUsage> = BTreeMap::new();
+    for (i, record) in reader.records().enumerate() {
+        let line = i + 2;
+        let record = record.map_err(|e| format!("line {line}: {e}"))?;
+        let field = |col: Option<usize>| col.and_then(|c| record.get(c)).unwrap_or("");
+        let number = |col: Option<usize>| -> Result<f64, String> {
+            let value = field(col).replace(',', "");
+            if value.is_empty() {
+                return Ok(0.0);
+            }
+            value
+                .parse::<f64>()
+                .map_err(|_| format!("line {line}: '{value}' is not a number"))
+        };
+
+        let date = parse_date(field(Some(date_col)))
+            .ok_or_else(|| format!("line {line}: invalid date '{}'", field(Some(date_col))))?;
+
+        let (model, mut input_tokens, mut output_tokens) = match model_col {
+            Some(_) => (field(model_col).to_string(), 0.0, 0.0),
+            None => {
+                let (model, is_output) = parse_azure_meter(field(meter_col));
+                let tokens = number(quantity_col)? * unit_multiplier(field(unit_col));
+                match is_output {
+                    Some(true) => (model, 0.0, tokens),
+                    Some(false) => (model, tokens, 0.0),
+                    // Not a token meter (e.g. hosting); cost only
+                    None => (model, 0.0, 0.0),
+                }
+            }
+        };
+        if model.is_empty() {
+            return Err(format!("line {line}: missing model"));
+        }
+        if model_col.is_some() {
+            if input_col.is_some() || output_col.is_some() {
+                input_tokens = number(input_col)?;
+                output_tokens = number(output_col)?;
+            } else if token_type_col.is_some() {
+                let tokens = number(quantity_col)?;
+                if field(token_type_col).to_lowercase().contains("output") {
+                    output_tokens = tokens;
+                } else {
+                    input_tokens = tokens;
+                }
+            }
+        }
+
+        let entry = usage
+            .entry((date, model.clone()))
+            .or_insert_with(|| ImportedUsage {
+                date,
+                model,
+                request_count: None,
+                input_tokens: 0,
+                output_tokens: 0,
+                cost_microcents: None,
+            });
+        entry.input_tokens += input_tokens.round() as i64;
+        entry.output_tokens += output_tokens.round() as i64;
+        if requests_col.is_some() {
+            entry.request_count =
+                Some(entry.request_count.unwrap_or(0) + number(requests_col)?.round() as i64);
+        }
+        if cost_col.is_some() {
+            entry.cost_microcents = Some(
+                entry.cost_microcents.unwrap_or(0)
+                    + crate::pricing::dollars_to_microcents(number(cost_col)?),
+            );
+        }
+    }
+    Ok(usage.into_values().collect())
+}
+
+#[cfg(not(feature = "csv-export"))]
+pub fn parse_usage_csv(_body: &str) -> Result<Vec<ImportedUsage>, String> {
+    Err("CSV imports require the 'csv-export' feature".to_string())
+}
+
+#[cfg(feature = "csv-export")]
+fn normalize_header(header: &str) -> String {
+    header
+        .trim_start_matches('\u{feff}')
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// Dates as `YYYY-MM-DD`, RFC 3339, `MM/DD/YYYY` (Azure) or Unix seconds.
+#[cfg(feature = "csv-export")]
+fn parse_date(value: &str) -> Option<chrono::NaiveDate> {
+    use chrono::{DateTime, NaiveDate};
+
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .or_else(|| {
+            DateTime::parse_from_rfc3339(value)
+                .ok()
+                .map(|d| d.date_naive())
+        })
+        .or_else(|| NaiveDate::parse_from_str(value, "%m/%d/%Y").ok())
+        .or_else(|| {
+            value
+                .parse::<i64>()
+                .ok()
+                .and_then(|s| DateTime::from_timestamp(s, 0))
+                .map(|d| d.date_naive())
+        })
+}
+
+/// Split an Azure OpenAI meter name into the model and whether it meters
+/// output tokens, e.g. `gpt-4o-0806-Outp-glbl 1K Tokens` is output for
+/// `gpt-4o-0806`. Meters that aren't input or output keep their full name.
+#[cfg(feature = "csv-export")]
+fn parse_azure_meter(meter: &str) -> (String, Option<bool>) {
+    let lower = meter.to_lowercase();
+    for (marker, is_output) in [
+        ("-outp", true),
+        (" output", true),
+        (" outp", true),
+        ("-inp", false),
+        (" input", false),
+        (" inp", false),
+    ] {
+        if let Some(pos) = lower.find(marker) {
+            return (meter[..pos].trim().to_string(), Some(is_output));
+        }
+    }
+    (meter.trim().to_string(), None)
+}
+
+#[cfg(feature = "csv-export")]
+fn unit_multiplier(unit: &str) -> f64 {
+    let unit = unit.to_lowercase();
+    if unit.starts_with("1m") || unit.contains("1,000,000") {
+        1_000_000.0
+    } else if unit.starts_with("1k") || unit.contains("1,000") {
+        1_000.0
+    } else {
+        1.0
+    }
+}
+
+#[cfg(all(test, feature = "csv-export"))]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_parse_openai_style_csv() {
+        let body = "start_time_iso,model,input_tokens,output_tokens,num_model_requests\n\
+                    2025-08-01T00:00:00+00:00,gpt-4o,1000,500,4\n\
+                    2025-08-01T00:00:00+00:00,gpt-4o,\"1,000\",0,1\n\
+                    2025-08-02T00:00:00+00:00,gpt-4o-mini,10,5,1\n";
+        let usage = parse_usage_csv(body).unwrap();
+        assert_eq!(usage.len(), 2);
+        assert_eq!(usage[0].date, date("2025-08-01"));
+        assert_eq!(usage[0].input_tokens, 2000);
+        assert_eq!(usage[0].request_count, Some(5));
+        assert_eq!(usage[0].cost_microcents, None);
+    }
+
+    #[test]
+    fn test_parse_token_type_csv() {
+        let body = "usage_date_utc,model_version,token_type,usage,cost_usd\n\
+                    2025-08-01,claude-sonnet-4,input_no_cache,100,0.30\n\
+                    2025-08-01,claude-sonnet-4,input_cache_read,400,0.12\n\
+                    2025-08-01,claude-sonnet-4,output,50,0.75\n";
+        let usage = parse_usage_csv(body).unwrap();
+        assert_eq!(usage.len(), 1);
+        assert_eq!(usage[0].input_tokens, 500);
+        assert_eq!(usage[0].output_tokens, 50);
+        assert_eq!(usage[0].request_count, None);
+        assert_eq!(usage[0].cost_microcents, Some(1_170_000));
+    }
+
+    #[test]
+    fn test_parse_azure_cost_export() {
+        let body = "Date,MeterName,Quantity,UnitOfMeasure,CostInBillingCurrency\n\
+                    08/01/2025,gpt-4o-0806-Inp-glbl 1K Tokens,12.5,1K,0.03\n\
+                    08/01/2025,gpt-4o-0806-Outp-glbl 1K Tokens,2,1K,0.02\n\
+                    08/01/2025,Fine-tuned Model Hosting,1,1 Hour,1.70\n";
+        let usage = parse_usage_csv(body).unwrap();
+        assert_eq!(usage.len(), 2);
+        let hosting = &usage[0];
+        assert_eq!(hosting.model, "Fine-tuned Model Hosting");
+        assert_eq!(hosting.input_tokens, 0);
+        let gpt = &usage[1];
+        assert_eq!(gpt.model, "gpt-4o-0806");
+        assert_eq!(gpt.input_tokens, 12_500);
+        assert_eq!(gpt.output_tokens, 2_000);
+        assert_eq!(gpt.cost_microcents, Some(50_000));
+    }
+
+    #[test]
+    fn test_parse_rejects_missing_columns_and_bad_values() {
+        assert!(parse_usage_csv("model,input_tokens\ngpt-4o,1\n").is_err());
+        let err = parse_usage_csv("date,model,input_tokens\n2025-08-01,gpt-4o,lots\n").unwrap_err();
+        assert!(err.contains("line 2"), "{err}");
+    }
+}
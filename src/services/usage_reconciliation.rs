@@ -0,0 +1,727 @@
+//! Usage reconciliation against provider-reported usage.
+//!
+//! A retried request whose first attempt timed out may still have completed
+//! upstream, so the provider can bill for more than the gateway recorded.
+//! Reports compare the gateway's daily request, token and cost totals,
+//! including the attempts behind them, with what each provider reported for
+//! the same days: live from its usage API, or from stored imports (see
+//! [`super::usage_imports`]).
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::{ProviderUsageApi, UsageReconciliationConfig, UsageReconciliationSource},
+    db::DateRange,
+    models::{DailyProviderAttempts, ImportedUsage, ImportedUsageRow, ProviderUsageImport},
+};
+
+const OPENAI_BASE_URL: &str = "https://api.openai.com";
+const ANTHROPIC_BASE_URL: &str = "https://api.anthropic.com";
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+/// Largest page of daily buckets both usage APIs accept.
+const MAX_DAILY_BUCKETS: u32 = 31;
+
+/// Gateway and provider-reported usage for one provider.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ProviderReconciliation {
+    /// Provider name
+    pub provider: String,
+    /// Where provider-reported usage came from (`openai`, `anthropic` or
+    /// `azure`), if anywhere
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Why provider-reported usage is missing, if the usage API call failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Totals across all days and models
+    pub totals: ReconciliationCounts,
+    /// Provider minus gateway totals
+    pub variance: UsageVariance,
+    /// Per day and model
+    pub days: Vec<ReconciliationRow>,
+}
+
+/// Usage for one provider, day and model.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ReconciliationRow {
+    pub date: NaiveDate,
+    pub model: String,
+    #[serde(flatten)]
+    pub counts: ReconciliationCounts,
+    /// Provider minus gateway counts
+    pub variance: UsageVariance,
+}
+
+/// Request, token and cost counts as seen by the gateway and by the provider.
+/// Provider fields are `null` when there is no provider-reported usage, and
+/// also for counts the source doesn't report (requests from Anthropic, cost
+/// from usage APIs).
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ReconciliationCounts {
+    /// Requests the gateway recorded
+    pub gateway_requests: i64,
+    /// Attempts the gateway sent upstream, counting retries
+    pub gateway_attempts: i64,
+    /// Attempts that timed out before a retry and may have been billed
+    pub suspected_duplicate_attempts: i64,
+    pub gateway_input_tokens: i64,
+    pub gateway_output_tokens: i64,
+    /// Cost the gateway recorded in microcents
+    pub gateway_cost_microcents: i64,
+    pub provider_requests: Option<i64>,
+    pub provider_input_tokens: Option<i64>,
+    pub provider_output_tokens: Option<i64>,
+    /// Cost the provider billed in microcents
+    pub provider_cost_microcents: Option<i64>,
+}
+
+impl ReconciliationCounts {
+    fn add(&mut self, other: &Self) {
+        self.gateway_requests += other.gateway_requests;
+        self.gateway_attempts += other.gateway_attempts;
+        self.suspected_duplicate_attempts += other.suspected_duplicate_attempts;
+        self.gateway_input_tokens += other.gateway_input_tokens;
+        self.gateway_output_tokens += other.gateway_output_tokens;
+        self.gateway_cost_microcents += other.gateway_cost_microcents;
+        self.provider_requests = add_opt(self.provider_requests, other.provider_requests);
+        self.provider_input_tokens =
+            add_opt(self.provider_input_tokens, other.provider_input_tokens);
+        self.provider_output_tokens =
+            add_opt(self.provider_output_tokens, other.provider_output_tokens);
+        self.provider_cost_microcents = add_opt(
+            self.provider_cost_microcents,
+            other.provider_cost_microcents,
+        );
+    }
+
+    fn add_reported(&mut self, usage: &ImportedUsage) {
+        self.provider_requests = add_opt(self.provider_requests, usage.request_count);
+        self.provider_input_tokens = add_opt(self.provider_input_tokens, Some(usage.input_tokens));
+        self.provider_output_tokens =
+            add_opt(self.provider_output_tokens, Some(usage.output_tokens));
+        self.provider_cost_microcents =
+            add_opt(self.provider_cost_microcents, usage.cost_microcents);
+    }
+
+    fn variance(&self) -> UsageVariance {
+        let cost_microcents = self
+            .provider_cost_microcents
+            .map(|p| p - self.gateway_cost_microcents);
+        UsageVariance {
+            requests: self.provider_requests.map(|p| p - self.gateway_requests),
+            input_tokens: self
+                .provider_input_tokens
+                .map(|p| p - self.gateway_input_tokens),
+            output_tokens: self
+                .provider_output_tokens
+                .map(|p| p - self.gateway_output_tokens),
+            cost_microcents,
+            cost_percent: cost_microcents
+                .filter(|_| self.gateway_cost_microcents != 0)
+                .map(|v| v as f64 * 100.0 / self.gateway_cost_microcents as f64),
+        }
+    }
+}
+
+/// Provider-reported minus gateway-recorded counts. Positive values mean the
+/// provider reported more than the gateway recorded. Each field is `null`
+/// when the provider side is.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct UsageVariance {
+    pub requests: Option<i64>,
+    pub input_tokens: Option<i64>,
+    pub output_tokens: Option<i64>,
+    pub cost_microcents: Option<i64>,
+    /// Cost variance as a percentage of gateway cost; `null` when the
+    /// gateway recorded no cost
+    pub cost_percent: Option<f64>,
+}
+
+fn add_opt(a: Option<i64>, b: Option<i64>) -> Option<i64> {
+    match (a, b) {
+        (None, None) => None,
+        (a, b) => Some(a.unwrap_or(0) + b.unwrap_or(0)),
+    }
+}
+
+/// Provider-reported usage for one provider, from its usage API or imports.
+#[derive(Debug)]
+pub struct ReportedUsage {
+    pub provider: String,
+    /// `openai`, `anthropic` or `azure`
+    pub source: String,
+    /// Inclusive date ranges the report covers. Gateway usage on a covered
+    /// day that the provider has no row for counts as reported zero.
+    pub covered: Vec<(NaiveDate, NaiveDate)>,
+    /// Whether the source reports request counts
+    pub reports_requests: bool,
+    /// Whether the source reports cost
+    pub reports_cost: bool,
+    pub usage: Result<Vec<ImportedUsage>, String>,
+}
+
+/// Build the reconciliation report, querying every configured usage API.
+/// A failing usage API is reported on its provider rather than failing the
+/// whole report.
+pub async fn reconcile(
+    http_client: &reqwest::Client,
+    config: &UsageReconciliationConfig,
+    gateway: Vec<DailyProviderAttempts>,
+    range: &DateRange,
+) -> Vec<ProviderReconciliation> {
+    let fetches = config.sources.iter().map(|source| async move {
+        let usage = fetch_reported_usage(http_client, source, range, config.timeout_secs).await;
+        if let Err(e) = &usage {
+            tracing::warn!(provider = %source.provider, error = %e, "Failed to fetch provider usage");
+        }
+        ReportedUsage {
+            provider: source.provider.clone(),
+            source: source.api.as_str().to_string(),
+            covered: vec![(range.start, range.end)],
+            reports_requests: source.api.reports_requests(),
+            reports_cost: false,
+            usage,
+        }
+    });
+    let reported = futures_util::future::join_all(fetches).await;
+    build_report(gateway, reported)
+}
+
+/// Build the reconciliation report from stored imports.
+///
+/// Each provider's source is that of its latest import, and only days some
+/// import covered count as reported.
+pub fn reconcile_imported(
+    gateway: Vec<DailyProviderAttempts>,
+    imports: Vec<ProviderUsageImport>,
+    usage: Vec<ImportedUsageRow>,
+) -> Vec<ProviderReconciliation> {
+    let mut reported: BTreeMap<String, ReportedUsage> = BTreeMap::new();
+    // Newest first, so the first import seen sets the source
+    for import in imports {
+        reported
+            .entry(import.provider.clone())
+            .or_insert_with(|| ReportedUsage {
+                provider: import.provider.clone(),
+                source: import.source.as_str().to_string(),
+                covered: Vec::new(),
+                reports_requests: false,
+                reports_cost: false,
+                usage: Ok(Vec::new()),
+            })
+            .covered
+            .push((import.start_date, import.end_date));
+    }
+    for row in usage {
+        let Some(provider) = reported.get_mut(&row.provider) else {
+            continue;
+        };
+        provider.reports_requests |= row.usage.request_count.is_some();
+        provider.reports_cost |= row.usage.cost_microcents.is_some();
+        if let Ok(rows) = &mut provider.usage {
+            rows.push(row.usage);
+        }
+    }
+    build_report(gateway, reported.into_values().collect())
+}
+
+/// Merge gateway rows with provider-reported usage by provider, day and
+/// model. Providers appear if either side has usage or reported at all.
+fn build_report(
+    gateway: Vec<DailyProviderAttempts>,
+    reported: Vec<ReportedUsage>,
+) -> Vec<ProviderReconciliation> {
+    let mut providers: BTreeMap<String, ProviderReconciliation> = BTreeMap::new();
+    let mut rows: HashMap<String, BTreeMap<(NaiveDate, String), ReconciliationCounts>> =
+        HashMap::new();
+
+    for row in gateway {
+        provider_entry(&mut providers, &row.provider);
+        let counts = rows
+            .entry(row.provider)
+            .or_default()
+            .entry((row.date, row.model))
+            .or_default();
+        counts.gateway_requests += row.request_count;
+        counts.gateway_attempts += row.provider_attempts;
+        counts.suspected_duplicate_attempts += row.suspected_duplicate_attempts;
+        counts.gateway_input_tokens += row.input_tokens;
+        counts.gateway_output_tokens += row.output_tokens;
+        counts.gateway_cost_microcents += row.cost_microcents;
+    }
+
+    for reported in reported {
+        let provider = provider_entry(&mut providers, &reported.provider);
+        provider.source = Some(reported.source);
+        match reported.usage {
+            Ok(usage) => {
+                let provider_rows = rows.entry(reported.provider).or_default();
+                // The provider reported nothing for these, rather than unknown
+                for ((date, _), counts) in provider_rows.iter_mut() {
+                    if !reported
+                        .covered
+                        .iter()
+                        .any(|(start, end)| start <= date && date <= end)
+                    {
+                        continue;
+                    }
+                    counts.provider_input_tokens = Some(0);
+                    counts.provider_output_tokens = Some(0);
+                    if reported.reports_requests {
+                        counts.provider_requests = Some(0);
+                    }
+                    if reported.reports_cost {
+                        counts.provider_cost_microcents = Some(0);
+                    }
+                }
+                for usage in usage {
+                    provider_rows
+                        .entry((usage.date, usage.model.clone()))
+                        .or_default()
+                        .add_reported(&usage);
+                }
+            }
+            Err(e) => provider.error = Some(e),
+        }
+    }
+
+    for (name, provider_rows) in rows {
+        let provider = provider_entry(&mut providers, &name);
+        for ((date, model), counts) in provider_rows {
+            provider.totals.add(&counts);
+            provider.days.push(ReconciliationRow {
+                date,
+                model,
+                variance: counts.variance(),
+                counts,
+            });
+        }
+        provider.variance = provider.totals.variance();
+    }
+    providers.into_values().collect()
+}
+
+fn provider_entry<'a>(
+    providers: &'a mut BTreeMap<String, ProviderReconciliation>,
+    name: &str,
+) -> &'a mut ProviderReconciliation {
+    providers
+        .entry(name.to_string())
+        .or_insert_with(|| ProviderReconciliation {
+            provider: name.to_string(),
+            source: None,
+            error: None,
+            totals: ReconciliationCounts::default(),
+            variance: UsageVariance::default(),
+            days: Vec::new(),
+        })
+}
+
+/// Fetch daily per-model usage for `range` from a provider's usage API.
+pub async fn fetch_reported_usage(
+    http_client: &reqwest::Client,
+    source: &UsageReconciliationSource,
+    range: &DateRange,
+    timeout_secs: u64,
+) -> Result<Vec<ImportedUsage>, String> {
+    let start = range.start.and_time(NaiveTime::MIN).and_utc();
+    let end = (range.end + chrono::Duration::days(1))
+        .and_time(NaiveTime::MIN)
+        .and_utc();
+    let timeout = std::time::Duration::from_secs(timeout_secs);
+
+    let mut usage = Vec::new();
+    let mut page: Option<String> = None;
+    loop {
+        let request = match source.api {
+            ProviderUsageApi::OpenAi => http_client
+                .get(format!(
+                    "{}/v1/organization/usage/completions",
+                    base_url(source, OPENAI_BASE_URL)
+                ))
+                .bearer_auth(&source.admin_api_key)
+                .query(&[
+                    ("start_time", start.timestamp().to_string()),
+                    ("end_time", end.timestamp().to_string()),
+                    ("bucket_width", "1d".to_string()),
+                    ("group_by", "model".to_string()),
+                    ("limit", MAX_DAILY_BUCKETS.to_string()),
+                ]),
+            ProviderUsageApi::Anthropic => http_client
+                .get(format!(
+                    "{}/v1/organizations/usage_report/messages",
+                    base_url(source, ANTHROPIC_BASE_URL)
+                ))
+                .header("x-api-key", &source.admin_api_key)
+                .header("anthropic-version", ANTHROPIC_VERSION)
+                .query(&[
+                    ("starting_at", start.to_rfc3339()),
+                    ("ending_at", end.to_rfc3339()),
+                    ("bucket_width", "1d".to_string()),
+                    ("group_by[]", "model".to_string()),
+                    ("limit", MAX_DAILY_BUCKETS.to_string()),
+                ]),
+        };
+        let request = match &page {
+            Some(page) => request.query(&[("page", page)]),
+            None => request,
+        };
+
+        let response = request
+            .timeout(timeout)
+            .send()
+            .await
+            .map_err(|e| format!("request failed: {e}"))?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!(
+                "usage API returned {status}: {}",
+                body.chars().take(200).collect::<String>()
+            ));
+        }
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| format!("failed to read response: {e}"))?;
+
+        let next_page = match source.api {
+            ProviderUsageApi::OpenAi => parse_openai_page(&body, &mut usage)?,
+            ProviderUsageApi::Anthropic => parse_anthropic_page(&body, &mut usage)?,
+        };
+        match next_page {
+            Some(next) => page = Some(next),
+            None => return Ok(usage),
+        }
+    }
+}
+
+fn base_url<'a>(source: &'a UsageReconciliationSource, default: &'a str) -> &'a str {
+    source
+        .base_url
+        .as_deref()
+        .unwrap_or(default)
+        .trim_end_matches('/')
+}
+
+#[derive(Deserialize)]
+struct UsagePage<B> {
+    data: Vec<B>,
+    #[serde(default)]
+    has_more: bool,
+    #[serde(default)]
+    next_page: Option<String>,
+}
+
+impl<B> UsagePage<B> {
+    fn next_page(&self) -> Option<String> {
+        self.has_more.then(|| self.next_page.clone()).flatten()
+    }
+}
+
+#[derive(Deserialize)]
+struct OpenAiBucket {
+    start_time: i64,
+    results: Vec<OpenAiResult>,
+}
+
+#[derive(Deserialize)]
+struct OpenAiResult {
+    model: Option<String>,
+    #[serde(default)]
+    input_tokens: i64,
+    #[serde(default)]
+    output_tokens: i64,
+    #[serde(default)]
+    num_model_requests: i64,
+}
+
+/// Parse one page of the OpenAI completions usage API, returning the next
+/// page cursor.
+fn parse_openai_page(
+    body: &[u8],
+    usage: &mut Vec<ImportedUsage>,
+) -> Result<Option<String>, String> {
+    let page: UsagePage<OpenAiBucket> =
+        serde_json::from_slice(body).map_err(|e| format!("invalid usage response: {e}"))?;
+    for bucket in &page.data {
+        let date = DateTime::<Utc>::from_timestamp(bucket.start_time, 0)
+            .ok_or_else(|| format!("invalid bucket start_time {}", bucket.start_time))?
+            .date_naive();
+        usage.extend(bucket.results.iter().map(|r| ImportedUsage {
+            date,
+            model: r.model.clone().unwrap_or_default(),
+            request_count: Some(r.num_model_requests),
+            input_tokens: r.input_tokens,
+            output_tokens: r.output_tokens,
+            cost_microcents: None,
+        }));
+    }
+    Ok(page.next_page())
+}
+
+#[derive(Deserialize)]
+struct AnthropicBucket {
+    starting_at: DateTime<Utc>,
+    results: Vec<AnthropicResult>,
+}
+
+#[derive(Deserialize)]
+struct AnthropicResult {
+    model: Option<String>,
+    #[serde(default)]
+    uncached_input_tokens: i64,
+    #[serde(default)]
+    cache_read_input_tokens: i64,
+    #[serde(default)]
+    cache_creation: AnthropicCacheCreation,
+    #[serde(default)]
+    output_tokens: i64,
+}
+
+#[derive(Default, Deserialize)]
+struct AnthropicCacheCreation {
+    #[serde(default)]
+    ephemeral_1h_input_tokens: i64,
+    #[serde(default)]
+    ephemeral_5m_input_tokens: i64,
+}
+
+/// Parse one page of the Anthropic messages usage report, returning the next
+/// page cursor. Input tokens include cache reads and writes, matching how the
+/// gateway records them; the report has no request counts.
+fn parse_anthropic_page(
+    body: &[u8],
+    usage: &mut Vec<ImportedUsage>,
+) -> Result<Option<String>, String> {
+    let page: UsagePage<AnthropicBucket> =
+        serde_json::from_slice(body).map_err(|e| format!("invalid usage response: {e}"))?;
+    for bucket in &page.data {
+        let date = bucket.starting_at.date_naive();
+        usage.extend(bucket.results.iter().map(|r| ImportedUsage {
+            date,
+            model: r.model.clone().unwrap_or_default(),
+            request_count: None,
+            input_tokens: r.uncached_input_tokens
+                + r.cache_read_input_tokens
+                + r.cache_creation.ephemeral_1h_input_tokens
+                + r.cache_creation.ephemeral_5m_input_tokens,
+            output_tokens: r.output_tokens,
+            cost_microcents: None,
+        }));
+    }
+    Ok(page.next_page())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_parse_openai_page() {
+        let body = br#"{
+            "object": "page",
+            "data": [{
+                "object": "bucket",
+                "start_time": 1730419200,
+                "end_time": 1730505600,
+                "results": [{
+                    "object": "organization.usage.completions.result",
+                    "input_tokens": 1000,
+                    "output_tokens": 500,
+                    "input_cached_tokens": 800,
+                    "num_model_requests": 5,
+                    "model": "gpt-4o-mini"
+                }]
+            }],
+            "has_more": true,
+            "next_page": "page_AAAA"
+        }"#;
+        let mut usage = Vec::new();
+        let next = parse_openai_page(body, &mut usage).unwrap();
+        assert_eq!(next.as_deref(), Some("page_AAAA"));
+        assert_eq!(
+            usage,
+            vec![ImportedUsage {
+                date: date("2024-11-01"),
+                model: "gpt-4o-mini".to_string(),
+                request_count: Some(5),
+                input_tokens: 1000,
+                output_tokens: 500,
+                cost_microcents: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_anthropic_page_counts_cache_tokens_as_input() {
+        let body = br#"{
+            "data": [{
+                "starting_at": "2025-08-01T00:00:00Z",
+                "ending_at": "2025-08-02T00:00:00Z",
+                "results": [{
+                    "uncached_input_tokens": 100,
+                    "cache_creation": {
+                        "ephemeral_1h_input_tokens": 10,
+                        "ephemeral_5m_input_tokens": 20
+                    },
+                    "cache_read_input_tokens": 300,
+                    "output_tokens": 50,
+                    "model": "claude-sonnet-4-20250514"
+                }]
+            }],
+            "has_more": false,
+            "next_page": null
+        }"#;
+        let mut usage = Vec::new();
+        assert_eq!(parse_anthropic_page(body, &mut usage).unwrap(), None);
+        assert_eq!(usage[0].date, date("2025-08-01"));
+        assert_eq!(usage[0].input_tokens, 430);
+        assert_eq!(usage[0].request_count, None);
+    }
+
+    fn gateway(provider: &str, model: &str, day: &str, requests: i64) -> DailyProviderAttempts {
+        DailyProviderAttempts {
+            date: date(day),
+            provider: provider.to_string(),
+            model: model.to_string(),
+            request_count: requests,
+            provider_attempts: requests,
+            suspected_duplicate_attempts: 0,
+            input_tokens: requests * 100,
+            output_tokens: requests * 50,
+            cost_microcents: requests * 1_000,
+        }
+    }
+
+    fn reported(day: &str, model: &str, requests: Option<i64>, input: i64) -> ImportedUsage {
+        ImportedUsage {
+            date: date(day),
+            model: model.to_string(),
+            request_count: requests,
+            input_tokens: input,
+            output_tokens: input / 2,
+            cost_microcents: None,
+        }
+    }
+
+    fn api_report(provider: &str, usage: Result<Vec<ImportedUsage>, String>) -> ReportedUsage {
+        ReportedUsage {
+            provider: provider.to_string(),
+            source: provider.to_string(),
+            covered: vec![(date("2025-08-01"), date("2025-08-31"))],
+            reports_requests: provider == "openai",
+            reports_cost: false,
+            usage,
+        }
+    }
+
+    #[test]
+    fn test_build_report_merges_gateway_and_provider_usage() {
+        let mut retried = gateway("openai", "gpt-4o", "2025-08-01", 10);
+        retried.provider_attempts = 12;
+        retried.suspected_duplicate_attempts = 2;
+        let reports = vec![
+            api_report(
+                "openai",
+                Ok(vec![
+                    reported("2025-08-01", "gpt-4o", Some(12), 1200),
+                    reported("2025-08-02", "gpt-4o", Some(1), 10),
+                ]),
+            ),
+            api_report("anthropic", Err("usage API returned 401".to_string())),
+        ];
+
+        let report = build_report(vec![retried], reports);
+        assert_eq!(report.len(), 2);
+
+        let anthropic = &report[0];
+        assert_eq!(anthropic.provider, "anthropic");
+        assert_eq!(anthropic.error.as_deref(), Some("usage API returned 401"));
+        assert!(anthropic.days.is_empty());
+
+        let openai = &report[1];
+        assert_eq!(openai.source.as_deref(), Some("openai"));
+        assert_eq!(openai.days.len(), 2);
+        assert_eq!(openai.days[0].counts.gateway_attempts, 12);
+        assert_eq!(openai.days[0].counts.provider_requests, Some(12));
+        assert_eq!(openai.days[0].variance.requests, Some(2));
+        assert_eq!(openai.days[0].variance.input_tokens, Some(200));
+        // Usage APIs report no cost
+        assert_eq!(openai.days[0].variance.cost_microcents, None);
+        // Provider-only day: billed upstream with nothing recorded by the gateway
+        assert_eq!(openai.days[1].counts.gateway_requests, 0);
+        assert_eq!(openai.totals.suspected_duplicate_attempts, 2);
+        assert_eq!(openai.totals.provider_requests, Some(13));
+        assert_eq!(openai.totals.provider_input_tokens, Some(1210));
+        assert_eq!(openai.variance.requests, Some(3));
+    }
+
+    #[test]
+    fn test_build_report_without_sources_has_no_provider_counts() {
+        let report = build_report(vec![gateway("vllm", "llama", "2025-08-01", 3)], Vec::new());
+        assert_eq!(report[0].source, None);
+        assert_eq!(report[0].totals.gateway_requests, 3);
+        assert_eq!(report[0].totals.provider_requests, None);
+        assert_eq!(report[0].variance, UsageVariance::default());
+    }
+
+    #[test]
+    fn test_build_report_zeroes_days_the_provider_did_not_report() {
+        let gateway = vec![gateway("anthropic", "claude-sonnet-4", "2025-08-01", 1)];
+        let report = build_report(gateway, vec![api_report("anthropic", Ok(Vec::new()))]);
+        let counts = &report[0].days[0].counts;
+        assert_eq!(counts.provider_input_tokens, Some(0));
+        // The Anthropic usage report has no request counts
+        assert_eq!(counts.provider_requests, None);
+    }
+
+    #[test]
+    fn test_reconcile_imported_only_counts_covered_days() {
+        use crate::models::{UsageImportMethod, UsageImportSource};
+
+        let import = ProviderUsageImport {
+            id: uuid::Uuid::new_v4(),
+            provider: "azure-east".to_string(),
+            source: UsageImportSource::Azure,
+            method: UsageImportMethod::Csv,
+            start_date: date("2025-08-01"),
+            end_date: date("2025-08-01"),
+            row_count: 1,
+            created_at: Utc::now(),
+        };
+        let usage = vec![ImportedUsageRow {
+            provider: "azure-east".to_string(),
+            import_id: import.id,
+            usage: ImportedUsage {
+                cost_microcents: Some(12_000),
+                ..reported("2025-08-01", "gpt-4o", None, 1000)
+            },
+        }];
+        let gateway = vec![
+            gateway("azure-east", "gpt-4o", "2025-08-01", 10),
+            // Not covered by any import
+            gateway("azure-east", "gpt-4o", "2025-08-02", 5),
+        ];
+
+        let report = reconcile_imported(gateway, vec![import], usage);
+        let azure = &report[0];
+        assert_eq!(azure.source.as_deref(), Some("azure"));
+        assert_eq!(azure.days[0].variance.cost_microcents, Some(2_000));
+        assert_eq!(azure.days[0].variance.cost_percent, Some(20.0));
+        assert_eq!(azure.days[1].counts.provider_cost_microcents, None);
+        assert_eq!(azure.totals.gateway_cost_microcents, 15_000);
+        assert_eq!(azure.totals.provider_cost_microcents, Some(12_000));
+    }
+}
@@ -0,0 +1,291 @@
+//! Reliability breakdowns of recorded usage.
+//!
+//! Spend endpoints answer "what did this cost"; these answer "how well did it
+//! work": latency percentiles, error counts by class and prompt cache hit
+//! rates per date, model, provider or project. Rows come from
+//! [`crate::db::repos::UsageRepo::get_daily_reliability`] and are rolled up
+//! here, with percentiles interpolated linearly within latency buckets.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::{DailyReliability, RELIABILITY_LATENCY_BUCKETS_MS};
+
+/// Dimension a reliability breakdown is grouped by.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ReliabilityDimension {
+    #[default]
+    Date,
+    Model,
+    Provider,
+    Project,
+}
+
+/// Latency, error and cache statistics for a set of requests.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ReliabilityStats {
+    pub request_count: i64,
+    /// Requests recorded with any error category
+    pub error_count: i64,
+    /// `error_count / request_count`
+    pub error_rate: f64,
+    /// Upstream failures (`provider_error`, `provider_unavailable`, `timeout`)
+    pub provider_errors: i64,
+    /// Requests blocked by a guardrail or provider safety filter
+    pub guardrail_blocks: i64,
+    /// Requests rejected by a gateway or provider rate limit
+    pub rate_limited: i64,
+    /// Requests that read prompt tokens from the provider's cache
+    pub cache_hits: i64,
+    /// `cache_hits / request_count`
+    pub cache_hit_rate: f64,
+    /// Estimated latency percentiles in milliseconds; `null` without any
+    /// recorded latencies
+    pub latency_p50_ms: Option<f64>,
+    pub latency_p95_ms: Option<f64>,
+    pub latency_p99_ms: Option<f64>,
+}
+
+/// Reliability statistics for one value of the breakdown dimension.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ReliabilityRow {
+    /// Date (YYYY-MM-DD), model, provider or project ID. `null` for usage
+    /// without a project when grouped by project.
+    pub key: Option<String>,
+    /// Project name when grouped by project
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(flatten)]
+    pub stats: ReliabilityStats,
+}
+
+/// Counts summed across rows, before rates and percentiles are derived.
+#[derive(Default)]
+struct Totals {
+    name: Option<String>,
+    request_count: i64,
+    error_count: i64,
+    provider_errors: i64,
+    guardrail_blocks: i64,
+    rate_limited: i64,
+    cache_hits: i64,
+    latency_count: i64,
+    latency_buckets: Vec<i64>,
+}
+
+impl Totals {
+    fn add(&mut self, row: &DailyReliability) {
+        self.request_count += row.request_count;
+        self.error_count += row.error_count;
+        self.provider_errors += row.provider_errors;
+        self.guardrail_blocks += row.guardrail_blocks;
+        self.rate_limited += row.rate_limited;
+        self.cache_hits += row.cache_hits;
+        self.latency_count += row.latency_count;
+        self.latency_buckets
+            .resize(RELIABILITY_LATENCY_BUCKETS_MS.len(), 0);
+        for (total, count) in self.latency_buckets.iter_mut().zip(&row.latency_buckets) {
+            *total += count;
+        }
+    }
+
+    fn stats(&self) -> ReliabilityStats {
+        let rate = |count: i64| {
+            if self.request_count == 0 {
+                0.0
+            } else {
+                count as f64 / self.request_count as f64
+            }
+        };
+        ReliabilityStats {
+            request_count: self.request_count,
+            error_count: self.error_count,
+            error_rate: rate(self.error_count),
+            provider_errors: self.provider_errors,
+            guardrail_blocks: self.guardrail_blocks,
+            rate_limited: self.rate_limited,
+            cache_hits: self.cache_hits,
+            cache_hit_rate: rate(self.cache_hits),
+            latency_p50_ms: self.latency_percentile(0.50),
+            latency_p95_ms: self.latency_percentile(0.95),
+            latency_p99_ms: self.latency_percentile(0.99),
+        }
+    }
+
+    /// Latency at `percentile` (0.0 to 1.0), interpolated within the bucket
+    /// holding it. Latencies past the last bucket report its bound.
+    fn latency_percentile(&self, percentile: f64) -> Option<f64> {
+        if self.latency_count == 0 {
+            return None;
+        }
+        let target = self.latency_count as f64 * percentile;
+        let mut prev_bound = 0.0;
+        let mut prev_count = 0.0;
+        for (&bound, &count) in RELIABILITY_LATENCY_BUCKETS_MS
+            .iter()
+            .zip(&self.latency_buckets)
+        {
+            let (bound, count) = (bound as f64, count as f64);
+            if count >= target {
+                if count == prev_count {
+                    return Some(prev_bound);
+                }
+                let fraction = (target - prev_count) / (count - prev_count);
+                return Some(prev_bound + fraction * (bound - prev_bound));
+            }
+            prev_bound = bound;
+            prev_count = count;
+        }
+        Some(prev_bound)
+    }
+}
+
+/// Statistics across all rows.
+pub fn totals(rows: &[DailyReliability]) -> ReliabilityStats {
+    let mut totals = Totals::default();
+    for row in rows {
+        totals.add(row);
+    }
+    totals.stats()
+}
+
+/// Statistics per value of `dimension`. Dates are in ascending order, other
+/// dimensions by request count, busiest first.
+pub fn breakdown(
+    rows: &[DailyReliability],
+    dimension: ReliabilityDimension,
+) -> Vec<ReliabilityRow> {
+    let mut groups: BTreeMap<Option<String>, Totals> = BTreeMap::new();
+    for row in rows {
+        let key = match dimension {
+            ReliabilityDimension::Date => Some(row.date.to_string()),
+            ReliabilityDimension::Model => Some(row.model.clone()),
+            ReliabilityDimension::Provider => Some(row.provider.clone()),
+            ReliabilityDimension::Project => row.project_id.map(|id| id.to_string()),
+        };
+        let totals = groups.entry(key).or_default();
+        if dimension == ReliabilityDimension::Project {
+            totals.name = row.project_name.clone();
+        }
+        totals.add(row);
+    }
+
+    let mut breakdown: Vec<ReliabilityRow> = groups
+        .into_iter()
+        .map(|(key, totals)| ReliabilityRow {
+            key,
+            name: totals.name.clone(),
+            stats: totals.stats(),
+        })
+        .collect();
+    if dimension != ReliabilityDimension::Date {
+        breakdown.sort_by(|a, b| b.stats.request_count.cmp(&a.stats.request_count));
+    }
+    breakdown
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn row(date: &str, model: &str, requests: i64, latencies_ms: &[i64]) -> DailyReliability {
+        DailyReliability {
+            date: NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+            provider: "openai".to_string(),
+            model: model.to_string(),
+            project_id: None,
+            project_name: None,
+            request_count: requests,
+            error_count: 0,
+            provider_errors: 0,
+            guardrail_blocks: 0,
+            rate_limited: 0,
+            cache_hits: 0,
+            latency_count: latencies_ms.len() as i64,
+            latency_buckets: RELIABILITY_LATENCY_BUCKETS_MS
+                .iter()
+                .map(|bound| latencies_ms.iter().filter(|&l| l <= bound).count() as i64)
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_totals_rates_and_percentiles() {
+        let rows = vec![
+            DailyReliability {
+                error_count: 3,
+                provider_errors: 1,
+                guardrail_blocks: 1,
+                rate_limited: 1,
+                cache_hits: 2,
+                ..row("2025-01-01", "gpt-4o", 10, &[200; 10])
+            },
+            row("2025-01-02", "gpt-4o", 10, &[200; 10]),
+        ];
+        let stats = totals(&rows);
+        assert_eq!(stats.request_count, 20);
+        assert_eq!(stats.error_count, 3);
+        assert_eq!(stats.provider_errors, 1);
+        assert!((stats.error_rate - 0.15).abs() < 1e-9);
+        assert!((stats.cache_hit_rate - 0.1).abs() < 1e-9);
+        // All latencies fall in the 100-250ms bucket
+        let p50 = stats.latency_p50_ms.unwrap();
+        assert!((100.0..=250.0).contains(&p50));
+        assert!(stats.latency_p99_ms.unwrap() <= 250.0);
+    }
+
+    #[test]
+    fn test_percentiles_spread_across_buckets() {
+        let mut latencies = vec![50; 90];
+        latencies.extend([4_000; 9]);
+        latencies.push(200_000);
+        let stats = totals(&[row("2025-01-01", "gpt-4o", 100, &latencies)]);
+        assert!(stats.latency_p50_ms.unwrap() <= 100.0);
+        let p95 = stats.latency_p95_ms.unwrap();
+        assert!((2_500.0..=5_000.0).contains(&p95));
+        assert!(stats.latency_p99_ms.unwrap() <= 5_000.0);
+
+        // Slower than the last bound reports that bound
+        let slow = totals(&[row("2025-01-01", "gpt-4o", 1, &[200_000])]);
+        assert_eq!(slow.latency_p50_ms, Some(120_000.0));
+        assert_eq!(totals(&[]).latency_p50_ms, None);
+    }
+
+    #[test]
+    fn test_breakdown_by_dimension() {
+        let project = Uuid::new_v4();
+        let rows = vec![
+            row("2025-01-02", "gpt-4o", 5, &[]),
+            row("2025-01-01", "gpt-4o-mini", 2, &[]),
+            DailyReliability {
+                project_id: Some(project),
+                project_name: Some("Search".to_string()),
+                ..row("2025-01-01", "gpt-4o", 3, &[])
+            },
+        ];
+
+        let by_date = breakdown(&rows, ReliabilityDimension::Date);
+        assert_eq!(by_date.len(), 2);
+        assert_eq!(by_date[0].key.as_deref(), Some("2025-01-01"));
+        assert_eq!(by_date[0].stats.request_count, 5);
+        assert_eq!(by_date[0].name, None);
+
+        let by_model = breakdown(&rows, ReliabilityDimension::Model);
+        assert_eq!(by_model[0].key.as_deref(), Some("gpt-4o"));
+        assert_eq!(by_model[0].stats.request_count, 8);
+
+        let by_project = breakdown(&rows, ReliabilityDimension::Project);
+        assert_eq!(by_project[0].key, None);
+        assert_eq!(by_project[0].stats.request_count, 7);
+        assert_eq!(by_project[1].key, Some(project.to_string()));
+        assert_eq!(by_project[1].name.as_deref(), Some("Search"));
+    }
+}
@@ -0,0 +1,108 @@
+//! Cost allocation tags on requests.
+//!
+//! Clients label requests with `X-Hadrian-Tags: team=search,feature=autocomplete`
+//! and the tags are stored on the request's usage records, so spend can be
+//! broken down by product feature or any other dimension that doesn't follow
+//! the org/team/project structure. Malformed pairs are dropped rather than
+//! failing the request: tags are for reporting, not for routing.
+
+use std::collections::BTreeMap;
+
+use axum::http::HeaderMap;
+
+/// Request header carrying cost allocation tags.
+pub const TAGS_HEADER: &str = "x-hadrian-tags";
+/// Most tags kept per request; later pairs are dropped.
+pub const MAX_TAGS: usize = 10;
+/// Longest accepted tag key.
+pub const MAX_KEY_LENGTH: usize = 64;
+/// Longest accepted tag value.
+pub const MAX_VALUE_LENGTH: usize = 128;
+
+/// Whether `key` is a valid tag key: 1-64 lowercase ASCII letters, digits,
+/// `_`, `-` or `.`. Keys are matched case-insensitively by lowercasing them
+/// on the way in.
+pub fn is_valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= MAX_KEY_LENGTH
+        && key.bytes().all(|b| {
+            b.is_ascii_lowercase() || b.is_ascii_digit() || matches!(b, b'_' | b'-' | b'.')
+        })
+}
+
+/// Parse a comma-separated list of `key=value` pairs. Keys are lowercased;
+/// later duplicates win. Pairs with an invalid key or an empty or overlong
+/// value are skipped.
+pub fn parse(header: &str) -> BTreeMap<String, String> {
+    let mut tags = BTreeMap::new();
+    for pair in header.split(',') {
+        let Some((key, value)) = pair.split_once('=') else {
+            continue;
+        };
+        let key = key.trim().to_ascii_lowercase();
+        let value = value.trim();
+        if !is_valid_key(&key) || value.is_empty() || value.len() > MAX_VALUE_LENGTH {
+            tracing::debug!(pair, "Ignoring invalid usage tag");
+            continue;
+        }
+        if tags.len() >= MAX_TAGS && !tags.contains_key(&key) {
+            tracing::debug!(pair, max = MAX_TAGS, "Ignoring usage tag over the limit");
+            continue;
+        }
+        tags.insert(key, value.to_string());
+    }
+    tags
+}
+
+/// The client's `X-Hadrian-Tags`, recorded with its usage. `None` when the
+/// header is missing or has no valid tags.
+pub fn from_headers(headers: &HeaderMap) -> Option<BTreeMap<String, String>> {
+    let tags = headers
+        .get_all(TAGS_HEADER)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .collect::<Vec<_>>()
+        .join(",");
+    Some(parse(&tags)).filter(|tags| !tags.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tags() {
+        let tags = parse(" Team=search , feature=autocomplete,feature=chat,bad,=x,y=,a b=c");
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags["team"], "search");
+        assert_eq!(tags["feature"], "chat");
+    }
+
+    #[test]
+    fn test_parse_tags_limits() {
+        let header = (0..15)
+            .map(|i| format!("k{i}=v"))
+            .collect::<Vec<_>>()
+            .join(",");
+        assert_eq!(parse(&header).len(), MAX_TAGS);
+
+        let long_value = format!("k={}", "v".repeat(MAX_VALUE_LENGTH + 1));
+        assert!(parse(&long_value).is_empty());
+        assert!(!is_valid_key(&"k".repeat(MAX_KEY_LENGTH + 1)));
+        assert!(is_valid_key("cost-center.v2_id"));
+    }
+
+    #[test]
+    fn test_from_headers() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(from_headers(&headers), None);
+        headers.append(TAGS_HEADER, "team=search".parse().unwrap());
+        headers.append(TAGS_HEADER, "feature=autocomplete".parse().unwrap());
+        let tags = from_headers(&headers).unwrap();
+        assert_eq!(tags["team"], "search");
+        assert_eq!(tags["feature"], "autocomplete");
+
+        headers.insert(TAGS_HEADER, "nonsense".parse().unwrap());
+        assert_eq!(from_headers(&headers), None);
+    }
+}
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use chrono::{NaiveDate, Utc};
 use uuid::Uuid;
 
+use super::FieldEncryptor;
 use crate::{
     db::{DateRange, DbPool, DbResult, ListParams, ListResult, UserDeletionResult},
     models::{
@@ -16,11 +17,21 @@ use crate::{
 #[derive(Clone)]
 pub struct UserService {
     db: Arc<DbPool>,
+    encryption: Option<Arc<FieldEncryptor>>,
 }
 
 impl UserService {
     pub fn new(db: Arc<DbPool>) -> Self {
-        Self { db }
+        Self {
+            db,
+            encryption: None,
+        }
+    }
+
+    /// Decrypt conversation content included in data exports.
+    pub fn with_encryption(mut self, encryption: Option<Arc<FieldEncryptor>>) -> Self {
+        self.encryption = encryption;
+        self
     }
 
     /// Create a new user
@@ -295,7 +306,13 @@ impl UserService {
                 )
                 .await?;
 
-            conversations.extend(result.items);
+            let mut items = result.items;
+            if let Some(enc) = &self.encryption {
+                for message in items.iter_mut().flat_map(|c| c.messages.iter_mut()) {
+                    message.content = enc.decrypt(&message.content)?;
+                }
+            }
+            conversations.extend(items);
 
             if !result.has_more {
                 break;
@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        DbPool, DbResult,
+        repos::{ListParams, ListResult},
+    },
+    models::VectorStoreSync,
+};
+
+/// Service layer for vector store sync run history
+#[derive(Clone)]
+pub struct VectorStoreSyncService {
+    db: Arc<DbPool>,
+}
+
+impl VectorStoreSyncService {
+    pub fn new(db: Arc<DbPool>) -> Self {
+        Self { db }
+    }
+
+    /// Get a sync run by ID
+    pub async fn get_by_id(&self, id: Uuid) -> DbResult<Option<VectorStoreSync>> {
+        self.db.vector_store_syncs().get_by_id(id).await
+    }
+
+    /// List sync runs for a vector store, newest first
+    pub async fn list(
+        &self,
+        vector_store_id: Uuid,
+        params: ListParams,
+    ) -> DbResult<ListResult<VectorStoreSync>> {
+        self.db
+            .vector_store_syncs()
+            .list(vector_store_id, params)
+            .await
+    }
+}
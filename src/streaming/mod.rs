@@ -21,7 +21,12 @@ use tokio::time::Sleep;
 #[cfg(feature = "server")]
 use tokio_util::task::TaskTracker;
 
-use crate::{db::DbPool, models::UsageLogEntry, observability::metrics, pricing::PricingConfig};
+use crate::{
+    db::DbPool,
+    models::UsageLogEntry,
+    observability::{metrics, slo},
+    pricing::PricingConfig,
+};
 
 /// Default capacity for the usage-drain channel.
 ///
@@ -54,6 +59,7 @@ pub struct UsageDrainHandle {
 struct UsageDrainJob {
     logger: Arc<UsageLogger>,
     tokens: Arc<TokenAccumulator>,
+    timing: StreamTiming,
 }
 
 #[cfg(feature = "server")]
@@ -63,7 +69,7 @@ impl UsageDrainHandle {
         let (tx, mut rx) = mpsc::channel::<UsageDrainJob>(capacity);
         task_tracker.spawn(async move {
             while let Some(job) = rx.recv().await {
-                job.logger.log_usage(&job.tokens).await;
+                job.logger.log_usage(&job.tokens, job.timing).await;
             }
             tracing::debug!("Usage drain channel closed; drainer exiting");
         });
@@ -73,8 +79,17 @@ impl UsageDrainHandle {
     /// Sync-send a usage log job. Safe to call from any thread/context,
     /// including `Drop`. Drops the job (with a warning) if the channel is
     /// full or closed — this is preferable to panicking from a destructor.
-    fn try_log(&self, logger: Arc<UsageLogger>, tokens: Arc<TokenAccumulator>) {
-        if let Err(err) = self.tx.try_send(UsageDrainJob { logger, tokens }) {
+    fn try_log(
+        &self,
+        logger: Arc<UsageLogger>,
+        tokens: Arc<TokenAccumulator>,
+        timing: StreamTiming,
+    ) {
+        if let Err(err) = self.tx.try_send(UsageDrainJob {
+            logger,
+            tokens,
+            timing,
+        }) {
             tracing::warn!(
                 error = %err,
                 "Usage drain channel rejected job; partial usage will not be recorded"
@@ -200,6 +215,102 @@ where
     }
 }
 
+// ============================================================================
+// Response Size Limit Stream
+// ============================================================================
+
+/// Error returned when a provider response exceeds its configured size limit.
+#[derive(Debug, thiserror::Error)]
+#[error("provider response exceeded {0} bytes")]
+pub struct ResponseTooLargeError(pub usize);
+
+/// A stream wrapper that counts response bytes from a provider and, when a
+/// limit is set, aborts the stream once the limit is crossed.
+///
+/// The total byte count is reported to the `llm_provider_payload_bytes`
+/// metric when the stream ends, errors, or is dropped early.
+pub struct ResponseSizeLimitStream<S> {
+    /// `None` once the stream has terminated.
+    inner: Option<S>,
+    provider: String,
+    limit: Option<usize>,
+    bytes: u64,
+    reported: bool,
+}
+
+impl<S> ResponseSizeLimitStream<S> {
+    pub fn new(inner: S, provider: String, limit: Option<usize>) -> Self {
+        Self {
+            inner: Some(inner),
+            provider,
+            limit,
+            bytes: 0,
+            reported: false,
+        }
+    }
+
+    fn report(&mut self) {
+        if !self.reported {
+            self.reported = true;
+            metrics::record_provider_payload_bytes(&self.provider, "response", self.bytes);
+        }
+    }
+}
+
+impl<S, E> Stream for ResponseSizeLimitStream<S>
+where
+    S: Stream<Item = Result<Bytes, E>> + Unpin,
+    E: From<io::Error>,
+{
+    type Item = Result<Bytes, E>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let Some(inner) = self.inner.as_mut() else {
+            return Poll::Ready(None);
+        };
+
+        match Pin::new(inner).poll_next(cx) {
+            Poll::Ready(Some(Ok(chunk))) => {
+                self.bytes += chunk.len() as u64;
+                if let Some(limit) = self.limit
+                    && self.bytes > limit as u64
+                {
+                    // Drop the upstream connection rather than draining it.
+                    self.inner = None;
+                    self.report();
+                    tracing::warn!(
+                        provider = %self.provider,
+                        limit_bytes = limit,
+                        "Provider response exceeded max_response_bytes - terminating stream"
+                    );
+                    metrics::record_provider_response_too_large(&self.provider);
+                    let err = io::Error::other(ResponseTooLargeError(limit));
+                    return Poll::Ready(Some(Err(err.into())));
+                }
+                Poll::Ready(Some(Ok(chunk)))
+            }
+            Poll::Ready(Some(Err(e))) => {
+                self.inner = None;
+                self.report();
+                Poll::Ready(Some(Err(e)))
+            }
+            Poll::Ready(None) => {
+                self.inner = None;
+                self.report();
+                Poll::Ready(None)
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<S> Drop for ResponseSizeLimitStream<S> {
+    fn drop(&mut self) {
+        // Client disconnects still count the bytes we pulled from upstream.
+        self.report();
+    }
+}
+
 // ============================================================================
 // SSE Parsing
 // ============================================================================
@@ -244,11 +355,17 @@ impl SseParser {
                         // Extract provider-reported cost (OpenRouter format)
                         let cost_dollars = usage.get("cost").and_then(|v| v.as_f64());
 
-                        // Extract cached tokens from input_tokens_details
-                        let cached_tokens = usage
+                        // Extract cache reads and writes from input_tokens_details
+                        // or prompt_tokens_details
+                        let input_details = usage
                             .get("input_tokens_details")
+                            .or_else(|| usage.get("prompt_tokens_details"));
+                        let cached_tokens = input_details
                             .and_then(|d| d.get("cached_tokens"))
                             .and_then(|v| v.as_i64());
+                        let cache_write_tokens = input_details
+                            .and_then(|d| d.get("cache_creation_input_tokens"))
+                            .and_then(|v| v.as_i64());
 
                         // Extract reasoning tokens from output_tokens_details
                         let reasoning_tokens = usage
@@ -279,6 +396,7 @@ impl SseParser {
                             completion_tokens,
                             cost_dollars,
                             cached_tokens,
+                            cache_write_tokens,
                             reasoning_tokens,
                             finish_reason,
                         });
@@ -322,6 +440,8 @@ pub enum SseChunk {
         cost_dollars: Option<f64>,
         /// Cached tokens (if reported)
         cached_tokens: Option<i64>,
+        /// Tokens written to the prompt cache (if reported)
+        cache_write_tokens: Option<i64>,
         /// Reasoning tokens (if reported)
         reasoning_tokens: Option<i64>,
         /// How the generation ended (stop, length, etc.)
@@ -396,6 +516,8 @@ pub struct TokenAccumulator {
     /// Stored with NONE_SENTINEL for None
     cached_tokens: AtomicI64,
     /// Stored with NONE_SENTINEL for None
+    cache_write_tokens: AtomicI64,
+    /// Stored with NONE_SENTINEL for None
     reasoning_tokens: AtomicI64,
     /// Provider-reported cost stored as nano-dollars (dollars * 1e9).
     /// Uses NONE_SENTINEL for None.
@@ -412,6 +534,7 @@ impl Default for TokenAccumulator {
             estimated_output: AtomicI64::new(0),
             usage_received: AtomicBool::new(false),
             cached_tokens: AtomicI64::new(NONE_SENTINEL),
+            cache_write_tokens: AtomicI64::new(NONE_SENTINEL),
             reasoning_tokens: AtomicI64::new(NONE_SENTINEL),
             provider_cost_nanodollars: AtomicI64::new(NONE_SENTINEL),
             finish_reason: crate::compat::Mutex::new(None),
@@ -425,6 +548,14 @@ impl TokenAccumulator {
         self.estimated_output.fetch_add(count, Ordering::Relaxed);
     }
 
+    /// Record prompt cache writes from the provider's final chunk. Call
+    /// before [`set_usage`](Self::set_usage), which publishes the usage.
+    pub fn set_cache_write_tokens(&self, cache_write_tokens: Option<i64>) {
+        if let Some(written) = cache_write_tokens {
+            self.cache_write_tokens.store(written, Ordering::Relaxed);
+        }
+    }
+
     /// Set the official usage data from the provider's final chunk
     pub fn set_usage(
         &self,
@@ -473,6 +604,16 @@ impl TokenAccumulator {
         self.estimated_output.load(Ordering::Relaxed)
     }
 
+    /// Get the official output token count, or the estimate if the provider
+    /// never sent usage data
+    pub fn final_output_tokens(&self) -> i64 {
+        if self.usage_received() {
+            self.output_tokens()
+        } else {
+            self.estimated_output()
+        }
+    }
+
     /// Check if official usage data was received from the provider
     pub fn usage_received(&self) -> bool {
         // Use Acquire ordering to synchronize with the Release in set_usage
@@ -489,6 +630,16 @@ impl TokenAccumulator {
         }
     }
 
+    /// Get the prompt cache write token count if available
+    pub fn cache_write_tokens(&self) -> Option<i64> {
+        let value = self.cache_write_tokens.load(Ordering::Relaxed);
+        if value == NONE_SENTINEL {
+            None
+        } else {
+            Some(value)
+        }
+    }
+
     /// Get the reasoning token count if available
     pub fn reasoning_tokens(&self) -> Option<i64> {
         let value = self.reasoning_tokens.load(Ordering::Relaxed);
@@ -539,6 +690,8 @@ pub struct StreamingMetrics {
     start_time: Instant,
     /// When the first chunk was received (stored as nanos since start)
     first_chunk_nanos: AtomicU64,
+    /// When the most recent chunk was received (stored as nanos since start)
+    last_chunk_nanos: AtomicU64,
     /// Total chunks received
     chunk_count: AtomicU64,
     /// Whether the first chunk has been received
@@ -547,8 +700,19 @@ pub struct StreamingMetrics {
     reported: AtomicBool,
 }
 
-/// Sentinel value indicating first chunk time is not set
-const FIRST_CHUNK_NOT_SET: u64 = u64::MAX;
+/// Sentinel value indicating a chunk time is not set
+const CHUNK_TIME_NOT_SET: u64 = u64::MAX;
+
+/// Per-stream latency and throughput, recorded on the usage log entry.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StreamTiming {
+    /// Time from the start of the stream to the first chunk
+    pub ttft: Option<Duration>,
+    /// Mean gap between consecutive chunks
+    pub inter_token_latency: Option<Duration>,
+    /// Output tokens divided by the time between the first and last chunk
+    pub output_tokens_per_second: Option<f64>,
+}
 
 impl StreamingMetrics {
     fn new(provider: String, model: String) -> Self {
@@ -556,7 +720,8 @@ impl StreamingMetrics {
             provider,
             model,
             start_time: Instant::now(),
-            first_chunk_nanos: AtomicU64::new(FIRST_CHUNK_NOT_SET),
+            first_chunk_nanos: AtomicU64::new(CHUNK_TIME_NOT_SET),
+            last_chunk_nanos: AtomicU64::new(CHUNK_TIME_NOT_SET),
             chunk_count: AtomicU64::new(0),
             first_chunk_received: AtomicBool::new(false),
             reported: AtomicBool::new(false),
@@ -569,17 +734,27 @@ impl StreamingMetrics {
         self.chunk_count.fetch_add(1, Ordering::Relaxed);
 
         // Record first chunk time if not already set
+        let elapsed_nanos = self.start_time.elapsed().as_nanos() as u64;
         if !self.first_chunk_received.swap(true, Ordering::AcqRel) {
-            let elapsed_nanos = self.start_time.elapsed().as_nanos() as u64;
             self.first_chunk_nanos
                 .store(elapsed_nanos, Ordering::Relaxed);
         }
+
+        // Record the gap since the previous chunk
+        let previous_nanos = self.last_chunk_nanos.swap(elapsed_nanos, Ordering::Relaxed);
+        if previous_nanos != CHUNK_TIME_NOT_SET {
+            metrics::record_streaming_inter_token_latency(
+                &self.provider,
+                &self.model,
+                elapsed_nanos.saturating_sub(previous_nanos) as f64 / 1_000_000_000.0,
+            );
+        }
     }
 
     /// Get time to first chunk in seconds, if first chunk was received
     fn time_to_first_chunk_secs(&self) -> Option<f64> {
         let nanos = self.first_chunk_nanos.load(Ordering::Relaxed);
-        if nanos == FIRST_CHUNK_NOT_SET {
+        if nanos == CHUNK_TIME_NOT_SET {
             None
         } else {
             Some(nanos as f64 / 1_000_000_000.0)
@@ -596,21 +771,59 @@ impl StreamingMetrics {
         self.chunk_count.load(Ordering::Relaxed)
     }
 
+    /// Summarize the stream's latency and throughput so far
+    fn timing(&self, output_tokens: i64) -> StreamTiming {
+        let first_nanos = self.first_chunk_nanos.load(Ordering::Relaxed);
+        let last_nanos = self.last_chunk_nanos.load(Ordering::Relaxed);
+        if first_nanos == CHUNK_TIME_NOT_SET || last_nanos == CHUNK_TIME_NOT_SET {
+            return StreamTiming::default();
+        }
+
+        let generation = Duration::from_nanos(last_nanos.saturating_sub(first_nanos));
+        let chunk_count = self.chunk_count();
+        StreamTiming {
+            ttft: Some(Duration::from_nanos(first_nanos)),
+            inter_token_latency: (chunk_count > 1).then(|| {
+                Duration::from_secs_f64(generation.as_secs_f64() / (chunk_count - 1) as f64)
+            }),
+            output_tokens_per_second: (output_tokens > 0 && !generation.is_zero())
+                .then(|| output_tokens as f64 / generation.as_secs_f64()),
+        }
+    }
+
     /// Report final streaming metrics
-    fn report(&self, outcome: &str) {
+    fn report(&self, outcome: &str, output_tokens: i64) {
         // Only report once
         if self.reported.swap(true, Ordering::AcqRel) {
             return;
         }
 
+        self.record(outcome, output_tokens);
+    }
+
+    /// Record streaming metrics and count the stream towards SLOs
+    fn record(&self, outcome: &str, output_tokens: i64) {
+        let time_to_first_chunk_secs = self.time_to_first_chunk_secs();
+        if let Some(tokens_per_second) = self.timing(output_tokens).output_tokens_per_second {
+            metrics::record_streaming_throughput(&self.provider, &self.model, tokens_per_second);
+        }
         metrics::record_streaming_response(
             &self.provider,
             &self.model,
             self.chunk_count(),
-            self.time_to_first_chunk_secs(),
+            time_to_first_chunk_secs,
             self.total_duration_secs(),
             outcome,
         );
+
+        // A cancelled stream has no end-to-end latency and is not a failure
+        slo::observe(slo::SloObservation {
+            provider: &self.provider,
+            model: &self.model,
+            ttft: time_to_first_chunk_secs.map(Duration::from_secs_f64),
+            latency: (outcome == "completed").then(|| self.start_time.elapsed()),
+            failed: outcome == "error",
+        });
     }
 }
 
@@ -619,14 +832,7 @@ impl Drop for StreamingMetrics {
         // If metrics weren't reported before drop, the stream was cancelled
         // (e.g., client disconnected, request timeout)
         if !*self.reported.get_mut() {
-            metrics::record_streaming_response(
-                &self.provider,
-                &self.model,
-                self.chunk_count(),
-                self.time_to_first_chunk_secs(),
-                self.total_duration_secs(),
-                "cancelled",
-            );
+            self.record("cancelled", 0);
         }
     }
 }
@@ -671,6 +877,7 @@ impl UsageLogger {
             completion_tokens,
             cost_dollars: provider_cost,
             cached_tokens,
+            cache_write_tokens,
             reasoning_tokens,
             ..
         } = usage
@@ -682,6 +889,7 @@ impl UsageLogger {
                     input_tokens: *prompt_tokens,
                     output_tokens: *completion_tokens,
                     cached_tokens: *cached_tokens,
+                    cache_write_tokens: *cache_write_tokens,
                     reasoning_tokens: *reasoning_tokens,
                     image_count: None,
                     image_size: None,
@@ -698,8 +906,8 @@ impl UsageLogger {
         }
     }
 
-    /// Log usage to database based on accumulated tokens
-    pub async fn log_usage(&self, tokens: &TokenAccumulator) {
+    /// Log usage to database based on accumulated tokens and stream timing
+    pub async fn log_usage(&self, tokens: &TokenAccumulator, timing: StreamTiming) {
         // Use official usage if received, otherwise use estimates
         let (input_tokens, output_tokens) = if tokens.usage_received() {
             (tokens.input_tokens(), tokens.output_tokens())
@@ -719,6 +927,7 @@ impl UsageLogger {
                 input_tokens,
                 output_tokens,
                 cached_tokens: tokens.cached_tokens(),
+                cache_write_tokens: tokens.cache_write_tokens(),
                 reasoning_tokens: tokens.reasoning_tokens(),
                 image_count: None,
                 image_size: None,
@@ -751,6 +960,13 @@ impl UsageLogger {
         entry.cached_tokens = saturate_i64_to_i32(tokens.cached_tokens().unwrap_or(0));
         entry.reasoning_tokens = saturate_i64_to_i32(tokens.reasoning_tokens().unwrap_or(0));
         entry.finish_reason = tokens.finish_reason();
+        entry.ttft_ms = timing
+            .ttft
+            .map(|ttft| ttft.as_millis().min(i32::MAX as u128) as i32);
+        entry.inter_token_latency_ms = timing
+            .inter_token_latency
+            .map(|latency| latency.as_secs_f64() * 1000.0);
+        entry.output_tokens_per_second = timing.output_tokens_per_second;
 
         // Log to database with retry logic, using task_tracker to ensure completion on shutdown
         let db = self.db.clone();
@@ -854,9 +1070,12 @@ where
                             completion_tokens,
                             cost_dollars,
                             cached_tokens,
+                            cache_write_tokens,
                             reasoning_tokens,
                             ref finish_reason,
                         } => {
+                            self.accumulated_tokens
+                                .set_cache_write_tokens(cache_write_tokens);
                             self.accumulated_tokens.set_usage(
                                 prompt_tokens,
                                 completion_tokens,
@@ -883,10 +1102,14 @@ where
                 // Stream ended normally - log usage and report metrics
                 if !self.stream_ended {
                     self.stream_ended = true;
-                    self.streaming_metrics.report("completed");
+                    let output_tokens = self.accumulated_tokens.final_output_tokens();
+                    self.streaming_metrics.report("completed", output_tokens);
                     #[cfg(feature = "server")]
-                    self.usage_drain
-                        .try_log(self.usage_logger.clone(), self.accumulated_tokens.clone());
+                    self.usage_drain.try_log(
+                        self.usage_logger.clone(),
+                        self.accumulated_tokens.clone(),
+                        self.streaming_metrics.timing(output_tokens),
+                    );
                 }
 
                 Poll::Ready(None)
@@ -895,12 +1118,16 @@ where
                 // Error in stream - still try to log what we have
                 if !self.stream_ended {
                     self.stream_ended = true;
-                    self.streaming_metrics.report("error");
+                    let output_tokens = self.accumulated_tokens.final_output_tokens();
+                    self.streaming_metrics.report("error", output_tokens);
                     #[cfg(feature = "server")]
                     {
                         tracing::warn!("Stream ended with error, logging partial usage");
-                        self.usage_drain
-                            .try_log(self.usage_logger.clone(), self.accumulated_tokens.clone());
+                        self.usage_drain.try_log(
+                            self.usage_logger.clone(),
+                            self.accumulated_tokens.clone(),
+                            self.streaming_metrics.timing(output_tokens),
+                        );
                     }
                 }
 
@@ -927,14 +1154,18 @@ impl<S> Drop for UsageTrackingStream<S> {
         // channel instead of spawning a task here directly.
         if !self.stream_ended {
             self.stream_ended = true;
-            self.streaming_metrics.report("dropped");
+            let output_tokens = self.accumulated_tokens.final_output_tokens();
+            self.streaming_metrics.report("dropped", output_tokens);
             #[cfg(feature = "server")]
             {
                 tracing::warn!(
                     "Stream dropped without completing - logging partial usage for budget accuracy"
                 );
-                self.usage_drain
-                    .try_log(self.usage_logger.clone(), self.accumulated_tokens.clone());
+                self.usage_drain.try_log(
+                    self.usage_logger.clone(),
+                    self.accumulated_tokens.clone(),
+                    self.streaming_metrics.timing(output_tokens),
+                );
             }
         }
     }
@@ -984,6 +1215,7 @@ mod tests {
                 completion_tokens,
                 cost_dollars,
                 cached_tokens,
+                cache_write_tokens,
                 reasoning_tokens,
                 finish_reason,
             }) => {
@@ -991,6 +1223,7 @@ mod tests {
                 assert_eq!(completion_tokens, 50);
                 assert!(cost_dollars.is_none());
                 assert!(cached_tokens.is_none());
+                assert!(cache_write_tokens.is_none());
                 assert!(reasoning_tokens.is_none());
                 assert!(finish_reason.is_none());
             }
@@ -1010,6 +1243,7 @@ mod tests {
                 completion_tokens,
                 cost_dollars,
                 cached_tokens,
+                cache_write_tokens,
                 reasoning_tokens,
                 finish_reason,
             }) => {
@@ -1017,6 +1251,7 @@ mod tests {
                 assert_eq!(completion_tokens, 7);
                 assert!((cost_dollars.unwrap() - 0.0000014).abs() < 1e-10);
                 assert_eq!(cached_tokens, Some(0));
+                assert!(cache_write_tokens.is_none());
                 assert_eq!(reasoning_tokens, Some(0));
                 assert!(finish_reason.is_none());
             }
@@ -1036,6 +1271,7 @@ mod tests {
                 completion_tokens,
                 cost_dollars,
                 cached_tokens,
+                cache_write_tokens,
                 reasoning_tokens,
                 finish_reason,
             }) => {
@@ -1043,6 +1279,7 @@ mod tests {
                 assert_eq!(completion_tokens, 57);
                 assert!((cost_dollars.unwrap() - 0.0000236).abs() < 1e-10);
                 assert_eq!(cached_tokens, Some(0));
+                assert!(cache_write_tokens.is_none());
                 assert_eq!(reasoning_tokens, Some(47));
                 // "completed" is mapped to "stop"
                 assert_eq!(finish_reason, Some("stop".to_string()));
@@ -1051,6 +1288,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_sse_usage_with_prompt_cache() {
+        // Chat completions format, as converted from Anthropic/Bedrock
+        let chunk = br#"data: {"choices":[],"usage":{"prompt_tokens":1200,"completion_tokens":30,"total_tokens":1230,"prompt_tokens_details":{"cached_tokens":1000,"cache_creation_input_tokens":150}}}"#;
+        let result = SseParser::parse_chunk(chunk);
+
+        match result {
+            Some(SseChunk::Usage {
+                prompt_tokens,
+                cached_tokens,
+                cache_write_tokens,
+                ..
+            }) => {
+                assert_eq!(prompt_tokens, 1200);
+                assert_eq!(cached_tokens, Some(1000));
+                assert_eq!(cache_write_tokens, Some(150));
+            }
+            _ => panic!("Expected Usage chunk"),
+        }
+    }
+
     #[test]
     fn test_parse_sse_usage_with_finish_reason() {
         // OpenAI format with finish_reason in choices
@@ -1147,6 +1405,7 @@ mod tests {
         acc.add_estimated_output(10);
         acc.add_estimated_output(5);
         assert_eq!(acc.estimated_output(), 15);
+        assert_eq!(acc.final_output_tokens(), 15);
 
         // Simulate official usage
         acc.set_usage(
@@ -1160,6 +1419,7 @@ mod tests {
         assert_eq!(acc.input_tokens(), 100);
         assert_eq!(acc.output_tokens(), 50);
         assert!(acc.usage_received());
+        assert_eq!(acc.final_output_tokens(), 50);
         assert_eq!(acc.cached_tokens(), Some(10));
         assert_eq!(acc.reasoning_tokens(), Some(5));
         assert_eq!(acc.finish_reason(), Some("stop".to_string()));
@@ -1265,6 +1525,33 @@ mod tests {
         assert_eq!(metrics.chunk_count(), 4);
     }
 
+    #[test]
+    fn test_streaming_metrics_timing() {
+        let metrics = StreamingMetrics::new("openai".to_string(), "gpt-4".to_string());
+        assert_eq!(metrics.timing(100), StreamTiming::default());
+
+        metrics.record_chunk();
+        let timing = metrics.timing(100);
+        assert!(timing.ttft.is_some());
+        // A single chunk has no gaps and no generation time to divide by
+        assert!(timing.inter_token_latency.is_none());
+        assert!(timing.output_tokens_per_second.is_none());
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        metrics.record_chunk();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        metrics.record_chunk();
+
+        let timing = metrics.timing(100);
+        let inter_token = timing.inter_token_latency.unwrap();
+        assert!(inter_token >= Duration::from_millis(10));
+        let tokens_per_second = timing.output_tokens_per_second.unwrap();
+        assert!(tokens_per_second > 0.0 && tokens_per_second <= 5000.0);
+
+        // No output tokens, no throughput
+        assert!(metrics.timing(0).output_tokens_per_second.is_none());
+    }
+
     #[test]
     fn test_streaming_metrics_total_duration() {
         let metrics = StreamingMetrics::new("bedrock".to_string(), "titan".to_string());
@@ -1289,12 +1576,12 @@ mod tests {
 
         // First report should succeed
         assert!(!metrics.reported.load(Ordering::Relaxed));
-        metrics.report("completed");
+        metrics.report("completed", 0);
         assert!(metrics.reported.load(Ordering::Relaxed));
 
         // Subsequent reports should be no-ops
-        metrics.report("error");
-        metrics.report("cancelled");
+        metrics.report("error", 0);
+        metrics.report("cancelled", 0);
         // If it tried to report again, it would have panicked or caused issues
         // The fact that we get here means it correctly skipped subsequent reports
     }
@@ -1354,7 +1641,7 @@ mod tests {
         let metrics = StreamingMetrics::new("openai".to_string(), "gpt-4".to_string());
         metrics.record_chunk();
 
-        metrics.report("completed");
+        metrics.report("completed", 10);
         assert!(metrics.reported.load(Ordering::Relaxed));
 
         // Drop should see reported=true and skip
@@ -0,0 +1,265 @@
+//! Parameter governance enforcement.
+//!
+//! Applies the [`ParameterGovernance`](crate::models::ParameterGovernance)
+//! rules an admin set on the caller's organization and project. Unlike the
+//! config-driven [`TransformPipeline`](super::TransformPipeline), rules are
+//! loaded per request from the database, and a rule can reject the request
+//! instead of rewriting it.
+
+use serde_json::Value;
+
+use crate::{
+    config::TransformEndpoint,
+    models::{GovernanceViolationAction, ParameterGovernanceRule},
+};
+
+/// Fields capped by a rule's `max_tokens`.
+const MAX_TOKEN_FIELDS: &[&str] = &["max_tokens", "max_completion_tokens", "max_output_tokens"];
+
+/// What enforcement did to a request that was let through.
+#[derive(Debug, Default, PartialEq)]
+pub struct GovernanceOutcome {
+    /// Violations fixed by `clamp` rules
+    pub clamped: Vec<String>,
+    /// Whether a default system prompt was added
+    pub prompt_added: bool,
+}
+
+impl GovernanceOutcome {
+    /// Whether the body was changed.
+    pub fn modified(&self) -> bool {
+        !self.clamped.is_empty() || self.prompt_added
+    }
+}
+
+/// Apply `rules` in order to `body`.
+///
+/// Rules whose `models` don't match the request's `model` are skipped.
+/// `clamp` rules fix violations in place; if any `reject` rule is violated,
+/// returns every such violation and the body must not be forwarded.
+pub fn enforce<'a>(
+    rules: impl IntoIterator<Item = &'a ParameterGovernanceRule>,
+    endpoint: TransformEndpoint,
+    body: &mut Value,
+) -> Result<GovernanceOutcome, Vec<String>> {
+    let model = body.get("model").and_then(Value::as_str).map(String::from);
+    let mut outcome = GovernanceOutcome::default();
+    let mut rejected = Vec::new();
+
+    for rule in rules {
+        if !rule.matches_model(model.as_deref()) {
+            continue;
+        }
+        let clamp = rule.on_violation == GovernanceViolationAction::Clamp;
+        let violations = check_rule(rule, body, clamp);
+        if clamp {
+            outcome.clamped.extend(violations);
+        } else {
+            rejected.extend(violations);
+        }
+        if let Some(prompt) = &rule.default_system_prompt {
+            outcome.prompt_added |= add_default_system_prompt(endpoint, body, prompt);
+        }
+    }
+
+    if rejected.is_empty() {
+        Ok(outcome)
+    } else {
+        Err(rejected)
+    }
+}
+
+/// Describe each way `body` breaks `rule`, fixing it when `clamp` is set.
+fn check_rule(rule: &ParameterGovernanceRule, body: &mut Value, clamp: bool) -> Vec<String> {
+    let mut violations = Vec::new();
+    let Some(obj) = body.as_object_mut() else {
+        return violations;
+    };
+
+    for param in &rule.forbidden_params {
+        if obj.contains_key(param) {
+            violations.push(format!("parameter '{param}' is not allowed"));
+            if clamp {
+                obj.remove(param);
+            }
+        }
+    }
+
+    if let Some(cap) = rule.max_tokens {
+        for field in MAX_TOKEN_FIELDS {
+            if let Some(value) = obj.get_mut(*field)
+                && let Some(requested) = value.as_u64()
+                && requested > cap
+            {
+                violations.push(format!("'{field}' {requested} exceeds the limit of {cap}"));
+                if clamp {
+                    *value = Value::from(cap);
+                }
+            }
+        }
+    }
+
+    if let Some(value) = obj.get_mut("temperature")
+        && let Some(temperature) = value.as_f64()
+    {
+        let bounded = temperature
+            .max(rule.min_temperature.unwrap_or(f64::NEG_INFINITY))
+            .min(rule.max_temperature.unwrap_or(f64::INFINITY));
+        if bounded != temperature {
+            violations.push(format!(
+                "'temperature' {temperature} is outside the allowed range {}",
+                temperature_range(rule)
+            ));
+            if clamp {
+                *value = Value::from(bounded);
+            }
+        }
+    }
+
+    violations
+}
+
+fn temperature_range(rule: &ParameterGovernanceRule) -> String {
+    match (rule.min_temperature, rule.max_temperature) {
+        (Some(min), Some(max)) => format!("{min}-{max}"),
+        (Some(min), None) => format!(">= {min}"),
+        (None, Some(max)) => format!("<= {max}"),
+        (None, None) => "(any)".to_string(),
+    }
+}
+
+/// Add `prompt` if the caller supplied no instructions of their own.
+/// Returns whether the body changed.
+fn add_default_system_prompt(endpoint: TransformEndpoint, body: &mut Value, prompt: &str) -> bool {
+    match endpoint {
+        TransformEndpoint::ChatCompletions => {
+            let Some(messages) = body.get_mut("messages").and_then(Value::as_array_mut) else {
+                return false;
+            };
+            let has_system = messages.iter().any(|m| {
+                matches!(
+                    m.get("role").and_then(Value::as_str),
+                    Some("system" | "developer")
+                )
+            });
+            if has_system {
+                return false;
+            }
+            messages.insert(
+                0,
+                serde_json::json!({ "role": "system", "content": prompt }),
+            );
+            true
+        }
+        TransformEndpoint::Responses => {
+            let Some(obj) = body.as_object_mut() else {
+                return false;
+            };
+            let has_instructions = obj
+                .get("instructions")
+                .and_then(Value::as_str)
+                .is_some_and(|s| !s.is_empty());
+            if has_instructions {
+                return false;
+            }
+            obj.insert(
+                "instructions".to_string(),
+                Value::String(prompt.to_string()),
+            );
+            true
+        }
+        TransformEndpoint::Completions | TransformEndpoint::Embeddings => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn rule(on_violation: GovernanceViolationAction) -> ParameterGovernanceRule {
+        ParameterGovernanceRule {
+            max_tokens: Some(1000),
+            min_temperature: Some(0.2),
+            max_temperature: Some(1.0),
+            forbidden_params: vec!["logit_bias".into()],
+            on_violation,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_clamp_fixes_violations() {
+        let mut body = json!({
+            "model": "gpt-4o",
+            "messages": [],
+            "max_completion_tokens": 5000,
+            "temperature": 1.7,
+            "logit_bias": {"50256": -100},
+        });
+        let outcome = enforce(
+            [&rule(GovernanceViolationAction::Clamp)],
+            TransformEndpoint::ChatCompletions,
+            &mut body,
+        )
+        .unwrap();
+        assert_eq!(outcome.clamped.len(), 3);
+        assert_eq!(body["max_completion_tokens"], 1000);
+        assert_eq!(body["temperature"], 1.0);
+        assert!(body.get("logit_bias").is_none());
+    }
+
+    #[test]
+    fn test_reject_reports_violations_and_leaves_missing_params() {
+        let reject = rule(GovernanceViolationAction::Reject);
+
+        let mut body = json!({"model": "gpt-4o", "messages": [], "temperature": 0.1});
+        let violations =
+            enforce([&reject], TransformEndpoint::ChatCompletions, &mut body).unwrap_err();
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].contains("temperature"), "{violations:?}");
+
+        let mut body = json!({"model": "gpt-4o", "messages": []});
+        let outcome = enforce([&reject], TransformEndpoint::ChatCompletions, &mut body).unwrap();
+        assert!(!outcome.modified());
+        assert_eq!(body, json!({"model": "gpt-4o", "messages": []}));
+    }
+
+    #[test]
+    fn test_rules_scoped_to_models() {
+        let scoped = ParameterGovernanceRule {
+            models: vec!["gpt-4*".into()],
+            ..rule(GovernanceViolationAction::Reject)
+        };
+        let mut body = json!({"model": "claude-sonnet-4", "max_tokens": 9000});
+        assert!(enforce([&scoped], TransformEndpoint::ChatCompletions, &mut body).is_ok());
+        let mut body = json!({"model": "gpt-4o", "max_tokens": 9000});
+        assert!(enforce([&scoped], TransformEndpoint::ChatCompletions, &mut body).is_err());
+    }
+
+    #[test]
+    fn test_default_system_prompt_only_when_absent() {
+        let rule = ParameterGovernanceRule {
+            default_system_prompt: Some("Be careful.".into()),
+            ..Default::default()
+        };
+
+        let mut body = json!({"model": "m", "messages": [{"role": "user", "content": "hi"}]});
+        let outcome = enforce([&rule], TransformEndpoint::ChatCompletions, &mut body).unwrap();
+        assert!(outcome.prompt_added);
+        assert_eq!(body["messages"][0]["content"], "Be careful.");
+
+        let mut body = json!({"model": "m", "messages": [
+            {"role": "developer", "content": "Mine"},
+            {"role": "user", "content": "hi"},
+        ]});
+        let outcome = enforce([&rule], TransformEndpoint::ChatCompletions, &mut body).unwrap();
+        assert!(!outcome.prompt_added);
+        assert_eq!(body["messages"].as_array().unwrap().len(), 2);
+
+        let mut body = json!({"model": "m", "input": "hi"});
+        enforce([&rule], TransformEndpoint::Responses, &mut body).unwrap();
+        assert_eq!(body["instructions"], "Be careful.");
+    }
+}
@@ -0,0 +1,765 @@
+//! Request and response transformation hooks.
+//!
+//! A [`TransformPipeline`] is built once at startup from
+//! `[features.transforms]` and run by the transforms middleware on the
+//! data-plane endpoints (chat completions, responses, completions,
+//! embeddings):
+//!
+//! ```text
+//! Request ──► auth / authz ──► request transforms ──► handler ──► provider
+//!                                                                  │
+//! Response ◄── response transforms + injected headers ◄────────────┘
+//! ```
+//!
+//! Static transforms (system prompt prepending, parameter clamping, field
+//! stripping, header injection) are plain JSON rewrites. Post-processing
+//! rules (redaction, Markdown stripping, link policies, length limits)
+//! rewrite the generated text, streamed or not; see [`postprocess`]. WASM
+//! plugins run the body through a sandboxed module and need the
+//! `transform-plugins` feature; see [`plugin`] for the module ABI.
+
+pub mod governance;
+#[cfg(feature = "transform-plugins")]
+pub mod plugin;
+pub mod postprocess;
+
+use std::sync::Arc;
+
+use http::{HeaderMap, HeaderName, HeaderValue};
+use serde_json::Value;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    config::{TransformAction, TransformEndpoint, TransformPhase, TransformsConfig},
+    models::model_matches_pattern,
+};
+
+/// Errors raised while building or running the pipeline.
+#[derive(Debug, Error)]
+pub enum TransformError {
+    #[error(
+        "transform '{rule}' uses a WASM plugin, but this build lacks the transform-plugins feature"
+    )]
+    PluginsUnavailable { rule: String },
+
+    #[error("failed to load WASM plugin for transform '{rule}': {message}")]
+    PluginLoad { rule: String, message: String },
+
+    #[error("transform '{rule}' failed: {message}")]
+    Plugin { rule: String, message: String },
+}
+
+/// What a transform knows about the request it's rewriting.
+#[derive(Debug, Clone)]
+pub struct TransformContext {
+    pub endpoint: TransformEndpoint,
+    /// `model` from the request body, as the caller sent it.
+    pub model: Option<String>,
+    pub org_id: Option<Uuid>,
+    /// Slug of the caller's organization. Only looked up when a rule is
+    /// scoped with `orgs`.
+    pub org_slug: Option<String>,
+    pub project_id: Option<Uuid>,
+}
+
+struct CompiledRule {
+    name: String,
+    models: Vec<String>,
+    endpoints: Vec<TransformEndpoint>,
+    orgs: Vec<String>,
+    action: CompiledAction,
+}
+
+enum CompiledAction {
+    PrependSystemPrompt(String),
+    ClampParams {
+        max_tokens: Option<u64>,
+        min_temperature: Option<f64>,
+        max_temperature: Option<f64>,
+        max_top_p: Option<f64>,
+    },
+    StripFields {
+        paths: Vec<Vec<String>>,
+        phase: TransformPhase,
+    },
+    InjectHeaders(Vec<(HeaderName, HeaderValue)>),
+    PostProcess(Arc<postprocess::PostProcessor>),
+    #[cfg(feature = "transform-plugins")]
+    Wasm {
+        plugin: Arc<plugin::WasmPlugin>,
+        phases: Vec<TransformPhase>,
+        fail_open: bool,
+    },
+}
+
+impl CompiledRule {
+    fn applies(&self, ctx: &TransformContext) -> bool {
+        if !self.endpoints.is_empty() && !self.endpoints.contains(&ctx.endpoint) {
+            return false;
+        }
+        if !self.orgs.is_empty()
+            && !ctx
+                .org_slug
+                .as_ref()
+                .is_some_and(|slug| self.orgs.contains(slug))
+        {
+            return false;
+        }
+        if self.models.is_empty() {
+            return true;
+        }
+        ctx.model
+            .as_deref()
+            .is_some_and(|model| self.models.iter().any(|p| model_matches_pattern(model, p)))
+    }
+
+    fn runs_in(&self, phase: TransformPhase) -> bool {
+        match &self.action {
+            CompiledAction::PrependSystemPrompt(_) | CompiledAction::ClampParams { .. } => {
+                phase == TransformPhase::Request
+            }
+            CompiledAction::StripFields { phase: p, .. } => *p == phase,
+            CompiledAction::InjectHeaders(_) => false,
+            CompiledAction::PostProcess(_) => phase == TransformPhase::Response,
+            #[cfg(feature = "transform-plugins")]
+            CompiledAction::Wasm { phases, .. } => phases.contains(&phase),
+        }
+    }
+}
+
+/// Ordered list of transform rules, compiled from config.
+pub struct TransformPipeline {
+    rules: Vec<CompiledRule>,
+}
+
+impl TransformPipeline {
+    /// Build the pipeline. Returns `Ok(None)` when transforms are disabled
+    /// or no rules are configured. WASM plugins are loaded and compiled here
+    /// so a broken module fails startup rather than the first request.
+    pub fn from_config(config: &TransformsConfig) -> Result<Option<Self>, TransformError> {
+        if !config.is_active() {
+            return Ok(None);
+        }
+
+        let mut rules = Vec::with_capacity(config.rules.len());
+        for (i, rule) in config.rules.iter().enumerate() {
+            let name = rule
+                .name
+                .clone()
+                .unwrap_or_else(|| format!("{}#{i}", rule.action.as_str()));
+            let action = match &rule.action {
+                TransformAction::PrependSystemPrompt { content } => {
+                    CompiledAction::PrependSystemPrompt(content.clone())
+                }
+                TransformAction::ClampParams {
+                    max_tokens,
+                    min_temperature,
+                    max_temperature,
+                    max_top_p,
+                } => CompiledAction::ClampParams {
+                    max_tokens: *max_tokens,
+                    min_temperature: *min_temperature,
+                    max_temperature: *max_temperature,
+                    max_top_p: *max_top_p,
+                },
+                TransformAction::StripFields { fields, phase } => CompiledAction::StripFields {
+                    paths: fields
+                        .iter()
+                        .map(|f| f.split('.').map(String::from).collect())
+                        .collect(),
+                    phase: *phase,
+                },
+                // Names and values are checked by config validation.
+                TransformAction::InjectHeaders { headers } => CompiledAction::InjectHeaders(
+                    headers
+                        .iter()
+                        .filter_map(|(name, value)| {
+                            Some((
+                                HeaderName::from_bytes(name.as_bytes()).ok()?,
+                                HeaderValue::from_str(value).ok()?,
+                            ))
+                        })
+                        .collect(),
+                ),
+                TransformAction::Redact { .. }
+                | TransformAction::StripMarkdown {}
+                | TransformAction::LinkPolicy { .. }
+                | TransformAction::MaxLength { .. } => CompiledAction::PostProcess(Arc::new(
+                    postprocess::PostProcessor::from_action(&rule.action)
+                        .expect("post-processing rule"),
+                )),
+                #[cfg(feature = "transform-plugins")]
+                TransformAction::Wasm {
+                    path,
+                    phases,
+                    fuel,
+                    fail_open,
+                } => {
+                    let wasm = plugin::WasmPlugin::load(path, *fuel).map_err(|message| {
+                        TransformError::PluginLoad {
+                            rule: name.clone(),
+                            message,
+                        }
+                    })?;
+                    if let Some(phase) = phases.iter().find(|p| !wasm.exports_phase(**p)) {
+                        return Err(TransformError::PluginLoad {
+                            rule: name,
+                            message: format!(
+                                "module does not export `{}`",
+                                plugin::export_name(*phase)
+                            ),
+                        });
+                    }
+                    CompiledAction::Wasm {
+                        plugin: Arc::new(wasm),
+                        phases: phases.clone(),
+                        fail_open: *fail_open,
+                    }
+                }
+                #[cfg(not(feature = "transform-plugins"))]
+                TransformAction::Wasm { .. } => {
+                    return Err(TransformError::PluginsUnavailable { rule: name });
+                }
+            };
+            rules.push(CompiledRule {
+                name,
+                models: rule.models.clone(),
+                endpoints: rule.endpoints.clone(),
+                orgs: rule.orgs.clone(),
+                action,
+            });
+        }
+
+        Ok(Some(Self { rules }))
+    }
+
+    /// Number of configured rules.
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Whether any rule could apply to `endpoint`. Lets the middleware skip
+    /// buffering bodies for endpoints no rule targets.
+    pub fn targets(&self, endpoint: TransformEndpoint) -> bool {
+        self.rules
+            .iter()
+            .any(|r| r.endpoints.is_empty() || r.endpoints.contains(&endpoint))
+    }
+
+    /// Whether any rule is scoped to organizations, so the middleware has to
+    /// look up the caller's org slug.
+    pub fn filters_orgs(&self) -> bool {
+        self.rules.iter().any(|r| !r.orgs.is_empty())
+    }
+
+    /// Whether any matching rule rewrites the response body.
+    pub fn has_response_rules(&self, ctx: &TransformContext) -> bool {
+        self.rules
+            .iter()
+            .any(|r| r.runs_in(TransformPhase::Response) && r.applies(ctx))
+    }
+
+    /// Matching post-processing rules, in order, for rewriting a streamed
+    /// response. Other response rules only see non-streaming bodies.
+    pub fn stream_post_processors(
+        &self,
+        ctx: &TransformContext,
+    ) -> Vec<Arc<postprocess::PostProcessor>> {
+        self.rules
+            .iter()
+            .filter(|r| r.applies(ctx))
+            .filter_map(|r| match &r.action {
+                CompiledAction::PostProcess(step) => Some(Arc::clone(step)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Apply request-phase rules to `body` in order.
+    pub async fn transform_request(
+        &self,
+        ctx: &TransformContext,
+        body: &mut Value,
+    ) -> Result<(), TransformError> {
+        self.run(TransformPhase::Request, ctx, body).await
+    }
+
+    /// Apply response-phase rules to `body` in order.
+    pub async fn transform_response(
+        &self,
+        ctx: &TransformContext,
+        body: &mut Value,
+    ) -> Result<(), TransformError> {
+        self.run(TransformPhase::Response, ctx, body).await
+    }
+
+    /// Add headers from matching `inject_headers` rules. Later rules win.
+    pub fn apply_headers(&self, ctx: &TransformContext, headers: &mut HeaderMap) {
+        for rule in self.rules.iter().filter(|r| r.applies(ctx)) {
+            if let CompiledAction::InjectHeaders(injected) = &rule.action {
+                for (name, value) in injected {
+                    headers.insert(name.clone(), value.clone());
+                }
+            }
+        }
+    }
+
+    async fn run(
+        &self,
+        phase: TransformPhase,
+        ctx: &TransformContext,
+        body: &mut Value,
+    ) -> Result<(), TransformError> {
+        for rule in self.rules.iter() {
+            if !rule.runs_in(phase) || !rule.applies(ctx) {
+                continue;
+            }
+            tracing::trace!(rule = %rule.name, ?phase, "Applying transform");
+            match &rule.action {
+                CompiledAction::PrependSystemPrompt(content) => {
+                    prepend_system_prompt(ctx.endpoint, body, content)
+                }
+                CompiledAction::ClampParams {
+                    max_tokens,
+                    min_temperature,
+                    max_temperature,
+                    max_top_p,
+                } => clamp_params(
+                    body,
+                    *max_tokens,
+                    *min_temperature,
+                    *max_temperature,
+                    *max_top_p,
+                ),
+                CompiledAction::StripFields { paths, .. } => {
+                    for path in paths {
+                        strip_path(body, path);
+                    }
+                }
+                CompiledAction::InjectHeaders(_) => {}
+                CompiledAction::PostProcess(step) => {
+                    postprocess::process_body(std::slice::from_ref(step), ctx.endpoint, body)
+                }
+                #[cfg(feature = "transform-plugins")]
+                CompiledAction::Wasm {
+                    plugin, fail_open, ..
+                } => match run_plugin(plugin, phase, ctx, body).await {
+                    Ok(Some(replacement)) => *body = replacement,
+                    Ok(None) => {}
+                    Err(message) if *fail_open => {
+                        tracing::warn!(
+                            rule = %rule.name,
+                            error = %message,
+                            "WASM transform failed; passing body through"
+                        );
+                    }
+                    Err(message) => {
+                        return Err(TransformError::Plugin {
+                            rule: rule.name.clone(),
+                            message,
+                        });
+                    }
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "transform-plugins")]
+async fn run_plugin(
+    plugin: &Arc<plugin::WasmPlugin>,
+    phase: TransformPhase,
+    ctx: &TransformContext,
+    body: &Value,
+) -> Result<Option<Value>, String> {
+    let input = serde_json::to_vec(&serde_json::json!({
+        "phase": phase,
+        "endpoint": ctx.endpoint.as_str(),
+        "model": ctx.model,
+        "org_id": ctx.org_id,
+        "project_id": ctx.project_id,
+        "body": body,
+    }))
+    .map_err(|e| e.to_string())?;
+
+    // Plugins are CPU-bound and fuel-limited; keep them off the async workers.
+    let plugin = Arc::clone(plugin);
+    let output = tokio::task::spawn_blocking(move || plugin.call(phase, &input))
+        .await
+        .map_err(|e| e.to_string())??;
+
+    output
+        .map(|bytes| {
+            serde_json::from_slice(&bytes).map_err(|e| format!("invalid JSON from plugin: {e}"))
+        })
+        .transpose()
+}
+
+/// Put `content` ahead of the caller's instructions.
+fn prepend_system_prompt(endpoint: TransformEndpoint, body: &mut Value, content: &str) {
+    match endpoint {
+        TransformEndpoint::ChatCompletions => {
+            if let Some(messages) = body.get_mut("messages").and_then(Value::as_array_mut) {
+                messages.insert(
+                    0,
+                    serde_json::json!({ "role": "system", "content": content }),
+                );
+            }
+        }
+        TransformEndpoint::Responses => {
+            let Some(obj) = body.as_object_mut() else {
+                return;
+            };
+            let instructions = match obj.get("instructions").and_then(Value::as_str) {
+                Some(existing) if !existing.is_empty() => format!("{content}\n\n{existing}"),
+                _ => content.to_string(),
+            };
+            obj.insert("instructions".to_string(), Value::String(instructions));
+        }
+        TransformEndpoint::Completions | TransformEndpoint::Embeddings => {}
+    }
+}
+
+/// Clamp sampling parameters the caller set.
+fn clamp_params(
+    body: &mut Value,
+    max_tokens: Option<u64>,
+    min_temperature: Option<f64>,
+    max_temperature: Option<f64>,
+    max_top_p: Option<f64>,
+) {
+    let Some(obj) = body.as_object_mut() else {
+        return;
+    };
+
+    if let Some(cap) = max_tokens {
+        for key in ["max_tokens", "max_completion_tokens", "max_output_tokens"] {
+            if let Some(value) = obj.get_mut(key)
+                && value.as_u64().is_some_and(|v| v > cap)
+            {
+                *value = Value::from(cap);
+            }
+        }
+    }
+
+    if let Some(value) = obj.get_mut("temperature")
+        && let Some(temperature) = value.as_f64()
+    {
+        let clamped = temperature
+            .max(min_temperature.unwrap_or(f64::NEG_INFINITY))
+            .min(max_temperature.unwrap_or(f64::INFINITY));
+        if clamped != temperature {
+            *value = Value::from(clamped);
+        }
+    }
+
+    if let Some(cap) = max_top_p
+        && let Some(value) = obj.get_mut("top_p")
+        && value.as_f64().is_some_and(|p| p > cap)
+    {
+        *value = Value::from(cap);
+    }
+}
+
+/// Remove the field at `path`. `*` fans out over array elements and object
+/// values.
+fn strip_path(value: &mut Value, path: &[String]) {
+    let Some((head, rest)) = path.split_first() else {
+        return;
+    };
+    match value {
+        Value::Object(map) if head == "*" => {
+            if rest.is_empty() {
+                map.clear();
+            } else {
+                map.values_mut().for_each(|v| strip_path(v, rest));
+            }
+        }
+        Value::Object(map) => {
+            if rest.is_empty() {
+                map.remove(head);
+            } else if let Some(child) = map.get_mut(head) {
+                strip_path(child, rest);
+            }
+        }
+        Value::Array(items) if head == "*" => {
+            if rest.is_empty() {
+                items.clear();
+            } else {
+                items.iter_mut().for_each(|v| strip_path(v, rest));
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn pipeline(toml: &str) -> TransformPipeline {
+        let config: TransformsConfig = toml::from_str(toml).unwrap();
+        config.validate().unwrap();
+        TransformPipeline::from_config(&config).unwrap().unwrap()
+    }
+
+    fn chat_ctx(model: &str) -> TransformContext {
+        TransformContext {
+            endpoint: TransformEndpoint::ChatCompletions,
+            model: Some(model.to_string()),
+            org_id: None,
+            org_slug: None,
+            project_id: None,
+        }
+    }
+
+    #[test]
+    fn test_disabled_or_empty_builds_nothing() {
+        let config: TransformsConfig = toml::from_str("enabled = false").unwrap();
+        assert!(TransformPipeline::from_config(&config).unwrap().is_none());
+        assert!(
+            TransformPipeline::from_config(&TransformsConfig::default())
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prepend_system_prompt_chat_and_responses() {
+        let pipeline = pipeline(
+            r#"
+            [[rules]]
+            type = "prepend_system_prompt"
+            content = "Be brief."
+            "#,
+        );
+
+        let mut chat = json!({"model": "gpt-4o", "messages": [{"role": "user", "content": "hi"}]});
+        pipeline
+            .transform_request(&chat_ctx("gpt-4o"), &mut chat)
+            .await
+            .unwrap();
+        assert_eq!(
+            chat["messages"][0],
+            json!({"role": "system", "content": "Be brief."})
+        );
+        assert_eq!(chat["messages"].as_array().unwrap().len(), 2);
+
+        let ctx = TransformContext {
+            endpoint: TransformEndpoint::Responses,
+            ..chat_ctx("gpt-4o")
+        };
+        let mut responses =
+            json!({"model": "gpt-4o", "input": "hi", "instructions": "Use French."});
+        pipeline
+            .transform_request(&ctx, &mut responses)
+            .await
+            .unwrap();
+        assert_eq!(responses["instructions"], "Be brief.\n\nUse French.");
+    }
+
+    #[tokio::test]
+    async fn test_clamp_params_only_touches_present_values() {
+        let pipeline = pipeline(
+            r#"
+            [[rules]]
+            type = "clamp_params"
+            max_tokens = 1000
+            min_temperature = 0.2
+            max_temperature = 1.0
+            max_top_p = 0.9
+            "#,
+        );
+
+        let mut body = json!({
+            "model": "gpt-4o",
+            "max_tokens": 5000,
+            "max_completion_tokens": 500,
+            "temperature": 1.7,
+            "top_p": 0.95
+        });
+        pipeline
+            .transform_request(&chat_ctx("gpt-4o"), &mut body)
+            .await
+            .unwrap();
+        assert_eq!(body["max_tokens"], 1000);
+        assert_eq!(body["max_completion_tokens"], 500);
+        assert_eq!(body["temperature"], 1.0);
+        assert_eq!(body["top_p"], 0.9);
+
+        let mut body = json!({"model": "gpt-4o", "temperature": 0.0});
+        pipeline
+            .transform_request(&chat_ctx("gpt-4o"), &mut body)
+            .await
+            .unwrap();
+        assert_eq!(body["temperature"], 0.2);
+        assert!(body.get("max_tokens").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_strip_fields_by_phase_with_wildcards() {
+        let pipeline = pipeline(
+            r#"
+            [[rules]]
+            type = "strip_fields"
+            fields = ["user", "metadata.trace"]
+
+            [[rules]]
+            type = "strip_fields"
+            phase = "response"
+            fields = ["system_fingerprint", "choices.*.logprobs"]
+            "#,
+        );
+        let ctx = chat_ctx("gpt-4o");
+
+        let mut request =
+            json!({"model": "gpt-4o", "user": "u1", "metadata": {"trace": "x", "keep": 1}});
+        pipeline
+            .transform_request(&ctx, &mut request)
+            .await
+            .unwrap();
+        assert!(request.get("user").is_none());
+        assert_eq!(request["metadata"], json!({"keep": 1}));
+
+        let mut response = json!({
+            "system_fingerprint": "fp",
+            "choices": [
+                {"index": 0, "logprobs": {"content": []}},
+                {"index": 1, "logprobs": null}
+            ]
+        });
+        assert!(pipeline.has_response_rules(&ctx));
+        pipeline
+            .transform_response(&ctx, &mut response)
+            .await
+            .unwrap();
+        assert_eq!(response, json!({"choices": [{"index": 0}, {"index": 1}]}));
+    }
+
+    #[tokio::test]
+    async fn test_rules_match_models_and_endpoints() {
+        let pipeline = pipeline(
+            r#"
+            [[rules]]
+            type = "strip_fields"
+            models = ["claude-*"]
+            endpoints = ["chat_completions"]
+            fields = ["user"]
+            "#,
+        );
+
+        let mut body = json!({"user": "u1"});
+        pipeline
+            .transform_request(&chat_ctx("gpt-4o"), &mut body)
+            .await
+            .unwrap();
+        assert_eq!(body["user"], "u1");
+
+        let ctx = TransformContext {
+            endpoint: TransformEndpoint::Completions,
+            ..chat_ctx("claude-sonnet")
+        };
+        pipeline.transform_request(&ctx, &mut body).await.unwrap();
+        assert_eq!(body["user"], "u1");
+        assert!(!pipeline.targets(TransformEndpoint::Embeddings));
+
+        pipeline
+            .transform_request(&chat_ctx("claude-sonnet"), &mut body)
+            .await
+            .unwrap();
+        assert!(body.get("user").is_none());
+    }
+
+    #[test]
+    fn test_inject_headers() {
+        let pipeline = pipeline(
+            r#"
+            [[rules]]
+            type = "inject_headers"
+            headers = { "X-Policy-Version" = "2" }
+            "#,
+        );
+        let mut headers = HeaderMap::new();
+        pipeline.apply_headers(&chat_ctx("gpt-4o"), &mut headers);
+        assert_eq!(headers.get("x-policy-version").unwrap(), "2");
+        assert!(!pipeline.has_response_rules(&chat_ctx("gpt-4o")));
+    }
+
+    #[tokio::test]
+    async fn test_post_processing_rules_scoped_to_orgs() {
+        let pipeline = pipeline(
+            r#"
+            [[rules]]
+            type = "redact"
+            orgs = ["acme"]
+            patterns = ["\\b\\d{16}\\b"]
+
+            [[rules]]
+            type = "max_length"
+            max_chars = 12
+            "#,
+        );
+        assert!(pipeline.filters_orgs());
+
+        let response = json!({"choices": [{"message": {"role": "assistant", "content": "Card 4111111111111111"}}]});
+
+        let mut body = response.clone();
+        pipeline
+            .transform_response(&chat_ctx("gpt-4o"), &mut body)
+            .await
+            .unwrap();
+        assert_eq!(body["choices"][0]["message"]["content"], "Card 4111111…");
+        assert_eq!(
+            pipeline.stream_post_processors(&chat_ctx("gpt-4o")).len(),
+            1
+        );
+
+        let acme = TransformContext {
+            org_slug: Some("acme".to_string()),
+            ..chat_ctx("gpt-4o")
+        };
+        let mut body = response;
+        assert!(pipeline.has_response_rules(&acme));
+        pipeline.transform_response(&acme, &mut body).await.unwrap();
+        assert_eq!(body["choices"][0]["message"]["content"], "Card [REDACT…");
+        assert_eq!(pipeline.stream_post_processors(&acme).len(), 2);
+    }
+
+    #[test]
+    fn test_invalid_rules_rejected() {
+        for toml in [
+            "[[rules]]\ntype = \"clamp_params\"",
+            "[[rules]]\ntype = \"clamp_params\"\nmin_temperature = 1.5\nmax_temperature = 1.0",
+            "[[rules]]\ntype = \"strip_fields\"\nfields = [\"a..b\"]",
+            "[[rules]]\ntype = \"inject_headers\"\nheaders = { \"bad header\" = \"x\" }",
+            "[[rules]]\ntype = \"prepend_system_prompt\"\ncontent = \"  \"",
+            "[[rules]]\ntype = \"redact\"\npatterns = [\"(unclosed\"]",
+            "[[rules]]\ntype = \"link_policy\"",
+            "[[rules]]\ntype = \"link_policy\"\ndeny_domains = [\"https://evil.com\"]",
+            "[[rules]]\ntype = \"max_length\"\nmax_chars = 0",
+            "[[rules]]\ntype = \"strip_markdown\"\norgs = [\"\"]",
+        ] {
+            let config: TransformsConfig = toml::from_str(toml).unwrap();
+            assert!(config.validate().is_err(), "expected error for {toml}");
+        }
+    }
+
+    #[cfg(not(feature = "transform-plugins"))]
+    #[test]
+    fn test_wasm_rule_requires_feature() {
+        let config: TransformsConfig =
+            toml::from_str("[[rules]]\ntype = \"wasm\"\npath = \"plugin.wasm\"").unwrap();
+        assert!(matches!(
+            TransformPipeline::from_config(&config),
+            Err(TransformError::PluginsUnavailable { .. })
+        ));
+    }
+}
@@ -0,0 +1,120 @@
+//! WebAssembly transform plugins.
+//!
+//! A plugin is a core WebAssembly module with no imports that exports:
+//!
+//! - `memory` — the linear memory used to exchange data
+//! - `alloc(len: i32) -> i32` — reserve `len` bytes and return their offset
+//! - `transform_request(ptr: i32, len: i32) -> i64` and/or
+//!   `transform_response(ptr: i32, len: i32) -> i64`
+//!
+//! The gateway allocates room for the input, writes a UTF-8 JSON envelope
+//! there and calls the phase export:
+//!
+//! ```json
+//! {"phase": "request", "endpoint": "chat_completions", "model": "gpt-4o",
+//!  "org_id": "…", "project_id": null, "body": { … }}
+//! ```
+//!
+//! The export returns `(ptr << 32) | len` locating the replacement body (JSON)
+//! in memory, or `0` to leave the body unchanged. Every call gets a fresh
+//! instance, so plugins can't carry state between requests, and runs with a
+//! fuel budget and a memory cap.
+
+use wasmtime::{Config, Engine, Instance, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+use crate::config::TransformPhase;
+
+/// Linear memory a single plugin instance may grow to.
+const MAX_MEMORY_BYTES: usize = 64 * 1024 * 1024;
+
+/// Export called for `phase`.
+pub fn export_name(phase: TransformPhase) -> &'static str {
+    match phase {
+        TransformPhase::Request => "transform_request",
+        TransformPhase::Response => "transform_response",
+    }
+}
+
+/// A compiled plugin module.
+pub struct WasmPlugin {
+    engine: Engine,
+    module: Module,
+    fuel: u64,
+}
+
+impl WasmPlugin {
+    /// Compile the module at `path`.
+    pub fn load(path: &str, fuel: u64) -> Result<Self, String> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config).map_err(|e| format!("{e:#}"))?;
+        let module = Module::from_file(&engine, path).map_err(|e| format!("{e:#}"))?;
+
+        if let Some(import) = module.imports().next() {
+            return Err(format!(
+                "plugins must not import anything (found `{}::{}`)",
+                import.module(),
+                import.name()
+            ));
+        }
+        for required in ["memory", "alloc"] {
+            if module.get_export(required).is_none() {
+                return Err(format!("module does not export `{required}`"));
+            }
+        }
+
+        Ok(Self {
+            engine,
+            module,
+            fuel,
+        })
+    }
+
+    /// Whether the module exports the function for `phase`.
+    pub fn exports_phase(&self, phase: TransformPhase) -> bool {
+        self.module.get_export(export_name(phase)).is_some()
+    }
+
+    /// Run the plugin over `input`. Returns `None` when the plugin leaves the
+    /// body unchanged. Blocking; call from a blocking task.
+    pub fn call(&self, phase: TransformPhase, input: &[u8]) -> Result<Option<Vec<u8>>, String> {
+        let err = |e: wasmtime::Error| format!("{e:#}");
+
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(MAX_MEMORY_BYTES)
+            .build();
+        let mut store: Store<StoreLimits> = Store::new(&self.engine, limits);
+        store.limiter(|limits| limits);
+        store.set_fuel(self.fuel).map_err(err)?;
+
+        let instance = Instance::new(&mut store, &self.module, &[]).map_err(err)?;
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or("module does not export `memory`")?;
+        let alloc = instance
+            .get_typed_func::<i32, i32>(&mut store, "alloc")
+            .map_err(err)?;
+        let transform = instance
+            .get_typed_func::<(i32, i32), i64>(&mut store, export_name(phase))
+            .map_err(err)?;
+
+        let len = i32::try_from(input.len()).map_err(|_| "input too large".to_string())?;
+        let ptr = alloc.call(&mut store, len).map_err(err)?;
+        memory
+            .write(&mut store, ptr as u32 as usize, input)
+            .map_err(|e| e.to_string())?;
+
+        let packed = transform.call(&mut store, (ptr, len)).map_err(err)? as u64;
+        if packed == 0 {
+            return Ok(None);
+        }
+
+        let out_ptr = (packed >> 32) as usize;
+        let out_len = (packed & 0xffff_ffff) as usize;
+        let mut output = vec![0u8; out_len];
+        memory
+            .read(&store, out_ptr, &mut output)
+            .map_err(|e| e.to_string())?;
+        Ok(Some(output))
+    }
+}
@@ -0,0 +1,845 @@
+//! Post-processing of generated text.
+//!
+//! `redact`, `strip_markdown`, `link_policy` and `max_length` rules rewrite
+//! the text a model generated rather than the JSON around it. Text is
+//! processed a line at a time so the same rules work on streamed responses:
+//! a [`TextStream`] holds back each choice's text until its line ends, and
+//! [`SseRewriter`] applies that to chat completion, completion and Responses
+//! API event streams. A pattern spanning two lines is therefore never
+//! matched, streamed or not.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, LazyLock},
+};
+
+use bytes::{Bytes, BytesMut};
+use regex::{Captures, NoExpand, Regex};
+use serde_json::{Value, json};
+
+use crate::{
+    config::{TransformAction, TransformEndpoint},
+    streaming::SseBuffer,
+};
+
+static HEADING: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s{0,3}#{1,6}\s+").unwrap());
+static BLOCK_QUOTE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s*(?:>\s?)+").unwrap());
+static HORIZONTAL_RULE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s{0,3}(?:(?:-\s*){3,}|(?:\*\s*){3,}|(?:_\s*){3,})$").unwrap());
+static IMAGE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"!\[([^\]]*)\]\([^)]*\)").unwrap());
+static MARKDOWN_LINK: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"\[([^\]]+)\]\(([^)\s]+)(?:\s+"[^"]*")?\)"#).unwrap());
+static HTML_TAG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"</?[A-Za-z][A-Za-z0-9-]*(?:\s[^<>]*)?/?>").unwrap());
+static INLINE_CODE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"`([^`]+)`").unwrap());
+static STRONG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\*\*([^*]+)\*\*|__([^_]+)__").unwrap());
+static EMPHASIS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\*([^*\s][^*]*?)\*|\b_([^_\s][^_]*?)_\b").unwrap());
+static STRIKETHROUGH: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"~~([^~]+)~~").unwrap());
+/// A Markdown link (label and URL captured) or a bare URL.
+static LINK_OR_URL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"\[([^\]]*)\]\(([^)\s]+)\)|https?://[^\s<>()\[\]"'`]+"#).unwrap()
+});
+
+/// A compiled post-processing rule.
+pub enum PostProcessor {
+    Redact {
+        patterns: Vec<Regex>,
+        replacement: String,
+    },
+    StripMarkdown,
+    LinkPolicy {
+        allow_domains: Vec<String>,
+        deny_domains: Vec<String>,
+        rewrite_domains: Vec<(String, String)>,
+        replacement: String,
+    },
+    MaxLength {
+        max_chars: usize,
+        suffix: String,
+    },
+}
+
+/// What a rule remembers between the lines of one text.
+#[derive(Debug, Default)]
+struct LineState {
+    /// Inside a fenced code block (`strip_markdown`).
+    in_fence: bool,
+    /// Characters let through so far (`max_length`).
+    chars: usize,
+    /// The text has been cut off (`max_length`).
+    truncated: bool,
+}
+
+impl PostProcessor {
+    /// Compile a post-processing rule, or `None` for other rule types.
+    pub fn from_action(action: &TransformAction) -> Option<Self> {
+        Some(match action {
+            // Patterns are checked by config validation.
+            TransformAction::Redact {
+                patterns,
+                replacement,
+            } => Self::Redact {
+                patterns: patterns.iter().filter_map(|p| Regex::new(p).ok()).collect(),
+                replacement: replacement.clone(),
+            },
+            TransformAction::StripMarkdown {} => Self::StripMarkdown,
+            TransformAction::LinkPolicy {
+                allow_domains,
+                deny_domains,
+                rewrite_domains,
+                replacement,
+            } => {
+                // Most specific domain first, so `docs.example.com` wins over
+                // `example.com`
+                let mut rewrite_domains: Vec<_> = rewrite_domains
+                    .iter()
+                    .map(|(from, to)| (from.to_lowercase(), to.to_lowercase()))
+                    .collect();
+                rewrite_domains.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then(a.0.cmp(&b.0)));
+                Self::LinkPolicy {
+                    allow_domains: allow_domains.iter().map(|d| d.to_lowercase()).collect(),
+                    deny_domains: deny_domains.iter().map(|d| d.to_lowercase()).collect(),
+                    rewrite_domains,
+                    replacement: replacement.clone(),
+                }
+            }
+            TransformAction::MaxLength { max_chars, suffix } => Self::MaxLength {
+                max_chars: *max_chars,
+                suffix: suffix.clone(),
+            },
+            _ => return None,
+        })
+    }
+
+    /// Process one line, including its trailing newline if it has one.
+    fn apply_line(&self, line: &str, state: &mut LineState) -> String {
+        match self {
+            Self::Redact {
+                patterns,
+                replacement,
+            } => {
+                let mut text = line.to_string();
+                for pattern in patterns {
+                    text = pattern
+                        .replace_all(&text, NoExpand(replacement))
+                        .into_owned();
+                }
+                text
+            }
+            Self::StripMarkdown => strip_markdown_line(line, state),
+            Self::LinkPolicy { .. } => LINK_OR_URL
+                .replace_all(line, |caps: &Captures| self.rewrite_link(caps))
+                .into_owned(),
+            Self::MaxLength { max_chars, suffix } => {
+                if state.truncated {
+                    return String::new();
+                }
+                let len = line.chars().count();
+                if state.chars + len <= *max_chars {
+                    state.chars += len;
+                    return line.to_string();
+                }
+                let mut text: String = line.chars().take(max_chars - state.chars).collect();
+                text.push_str(suffix);
+                state.chars = *max_chars;
+                state.truncated = true;
+                text
+            }
+        }
+    }
+
+    /// Replacement for one [`LINK_OR_URL`] match under a link policy.
+    fn rewrite_link(&self, caps: &Captures) -> String {
+        let Self::LinkPolicy { replacement, .. } = self else {
+            return caps[0].to_string();
+        };
+        if let Some(url) = caps.get(2) {
+            let label = &caps[1];
+            return match self.link_target(url.as_str()) {
+                Some(url) => format!("[{label}]({url})"),
+                None => label.to_string(),
+            };
+        }
+        // Sentence punctuation after a bare URL isn't part of it
+        let matched = &caps[0];
+        let url = matched.trim_end_matches(['.', ',', ';', ':', '!', '?']);
+        let tail = &matched[url.len()..];
+        match self.link_target(url) {
+            Some(url) => format!("{url}{tail}"),
+            None => format!("{replacement}{tail}"),
+        }
+    }
+
+    /// Where a link should point, or `None` to remove it. Relative links and
+    /// URLs without a host are left alone.
+    fn link_target(&self, link: &str) -> Option<String> {
+        let Self::LinkPolicy {
+            allow_domains,
+            deny_domains,
+            rewrite_domains,
+            ..
+        } = self
+        else {
+            return Some(link.to_string());
+        };
+        let Some(mut url) = url::Url::parse(link).ok() else {
+            return Some(link.to_string());
+        };
+        let Some(host) = url.host_str().map(str::to_lowercase) else {
+            return Some(link.to_string());
+        };
+
+        if deny_domains.iter().any(|d| domain_matches(&host, d)) {
+            return None;
+        }
+        if !allow_domains.is_empty() && !allow_domains.iter().any(|d| domain_matches(&host, d)) {
+            return None;
+        }
+        let Some((from, to)) = rewrite_domains
+            .iter()
+            .find(|(from, _)| domain_matches(&host, from))
+        else {
+            return Some(link.to_string());
+        };
+        let rewritten = format!("{}{to}", &host[..host.len() - from.len()]);
+        if url.set_host(Some(&rewritten)).is_err() {
+            return Some(link.to_string());
+        }
+        Some(url.to_string())
+    }
+}
+
+/// Whether `host` is `domain` or one of its subdomains.
+fn domain_matches(host: &str, domain: &str) -> bool {
+    host == domain
+        || host
+            .strip_suffix(domain)
+            .is_some_and(|prefix| prefix.ends_with('.'))
+}
+
+fn strip_markdown_line(line: &str, state: &mut LineState) -> String {
+    let body = line.trim_end_matches(['\n', '\r']);
+    let newline = &line[body.len()..];
+    let trimmed = body.trim_start();
+    if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+        state.in_fence = !state.in_fence;
+        return String::new();
+    }
+    if state.in_fence {
+        return line.to_string();
+    }
+    if HORIZONTAL_RULE.is_match(body) {
+        return newline.to_string();
+    }
+
+    let text = HEADING.replace(body, "");
+    let text = BLOCK_QUOTE.replace(&text, "");
+    let text = IMAGE.replace_all(&text, "${1}");
+    let text = MARKDOWN_LINK.replace_all(&text, |caps: &Captures| {
+        if caps[1] == caps[2] {
+            caps[2].to_string()
+        } else {
+            format!("{} ({})", &caps[1], &caps[2])
+        }
+    });
+    let text = HTML_TAG.replace_all(&text, "");
+    let text = INLINE_CODE.replace_all(&text, "${1}");
+    let text = STRONG.replace_all(&text, "${1}${2}");
+    let text = EMPHASIS.replace_all(&text, "${1}${2}");
+    let text = STRIKETHROUGH.replace_all(&text, "${1}");
+    format!("{text}{newline}")
+}
+
+/// Post-processes one text as it arrives.
+///
+/// Text is held back until its line ends; [`finish`](Self::finish) releases
+/// the last, unterminated line.
+pub struct TextStream {
+    steps: Vec<(Arc<PostProcessor>, LineState)>,
+    pending: String,
+}
+
+impl TextStream {
+    pub fn new(steps: &[Arc<PostProcessor>]) -> Self {
+        Self {
+            steps: steps
+                .iter()
+                .map(|step| (Arc::clone(step), LineState::default()))
+                .collect(),
+            pending: String::new(),
+        }
+    }
+
+    /// Add text, returning the processed text of the lines it completes.
+    pub fn push(&mut self, text: &str) -> String {
+        self.pending.push_str(text);
+        let Some(end) = self.pending.rfind('\n') else {
+            return String::new();
+        };
+        let complete: String = self.pending.drain(..=end).collect();
+        complete
+            .split_inclusive('\n')
+            .map(|line| self.process_line(line))
+            .collect()
+    }
+
+    /// Process whatever text is still held back.
+    pub fn finish(&mut self) -> String {
+        let rest = std::mem::take(&mut self.pending);
+        if rest.is_empty() {
+            return String::new();
+        }
+        self.process_line(&rest)
+    }
+
+    fn process_line(&mut self, line: &str) -> String {
+        let mut text = line.to_string();
+        for (step, state) in &mut self.steps {
+            text = step.apply_line(&text, state);
+        }
+        text
+    }
+}
+
+/// Post-process a complete text.
+pub fn process_text(steps: &[Arc<PostProcessor>], text: &str) -> String {
+    let mut stream = TextStream::new(steps);
+    let mut processed = stream.push(text);
+    processed.push_str(&stream.finish());
+    processed
+}
+
+/// Post-process the generated text in a non-streaming response body: chat
+/// completion message content, completion text, or the Responses API's
+/// `output_text` parts. Tool calls and other fields are left alone.
+pub fn process_body(steps: &[Arc<PostProcessor>], endpoint: TransformEndpoint, body: &mut Value) {
+    match endpoint {
+        TransformEndpoint::ChatCompletions => {
+            for choice in choices_mut(body) {
+                match choice.pointer_mut("/message/content") {
+                    Some(Value::String(text)) => *text = process_text(steps, text),
+                    Some(Value::Array(parts)) => {
+                        for part in parts
+                            .iter_mut()
+                            .filter(|p| p.get("type").and_then(Value::as_str) == Some("text"))
+                        {
+                            process_field(steps, part, "text");
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        TransformEndpoint::Completions => {
+            for choice in choices_mut(body) {
+                process_field(steps, choice, "text");
+            }
+        }
+        TransformEndpoint::Responses => {
+            if let Some(items) = body.get_mut("output").and_then(Value::as_array_mut) {
+                for item in items {
+                    process_output_item(steps, item);
+                }
+            }
+        }
+        TransformEndpoint::Embeddings => {}
+    }
+}
+
+fn choices_mut(body: &mut Value) -> impl Iterator<Item = &mut Value> {
+    body.get_mut("choices")
+        .and_then(Value::as_array_mut)
+        .into_iter()
+        .flatten()
+}
+
+/// Post-process the `output_text` parts of a Responses API output message.
+fn process_output_item(steps: &[Arc<PostProcessor>], item: &mut Value) {
+    if item.get("type").and_then(Value::as_str) != Some("message") {
+        return;
+    }
+    if let Some(parts) = item.get_mut("content").and_then(Value::as_array_mut) {
+        for part in parts {
+            process_output_part(steps, part);
+        }
+    }
+}
+
+fn process_output_part(steps: &[Arc<PostProcessor>], part: &mut Value) {
+    if part.get("type").and_then(Value::as_str) == Some("output_text") {
+        process_field(steps, part, "text");
+    }
+}
+
+fn process_field(steps: &[Arc<PostProcessor>], value: &mut Value, field: &str) {
+    if let Some(Value::String(text)) = value.get_mut(field) {
+        *text = process_text(steps, text);
+    }
+}
+
+/// Rewrites a streamed response's server-sent events, post-processing the
+/// text deltas as they pass.
+///
+/// Each choice (or Responses API content part) gets its own [`TextStream`].
+/// Text held back when a choice finishes is added to its final chunk; for
+/// the Responses API it is sent as an extra `response.output_text.delta`
+/// event before `response.output_text.done`. The final texts in `done` and
+/// `completed` events are processed as a whole, so they match the deltas.
+pub struct SseRewriter {
+    endpoint: TransformEndpoint,
+    steps: Vec<Arc<PostProcessor>>,
+    buffer: SseBuffer,
+    /// Text streams by choice index, or by output and content index for the
+    /// Responses API.
+    texts: HashMap<(u64, u64), TextStream>,
+    /// Last chunk seen, used as a template when text has to be flushed on
+    /// its own.
+    last_chunk: Option<Value>,
+}
+
+impl SseRewriter {
+    pub fn new(endpoint: TransformEndpoint, steps: Vec<Arc<PostProcessor>>) -> Self {
+        Self {
+            endpoint,
+            steps,
+            buffer: SseBuffer::new(),
+            texts: HashMap::new(),
+            last_chunk: None,
+        }
+    }
+
+    /// Add bytes from the upstream stream, returning the rewritten complete
+    /// events among them.
+    pub fn push(&mut self, bytes: &[u8]) -> Bytes {
+        self.buffer.extend(bytes);
+        let mut out = BytesMut::new();
+        for event in self.buffer.extract_complete_events() {
+            self.rewrite_event(&event, &mut out);
+        }
+        out.freeze()
+    }
+
+    /// Flush at the end of the stream: any text still held back, then any
+    /// trailing bytes that didn't form a complete event.
+    pub fn finish(&mut self) -> Bytes {
+        let mut out = BytesMut::new();
+        self.flush_choices(&mut out);
+        out.extend_from_slice(&self.buffer.take_remaining());
+        out.freeze()
+    }
+
+    fn rewrite_event(&mut self, event: &[u8], out: &mut BytesMut) {
+        let Ok(text) = std::str::from_utf8(event) else {
+            out.extend_from_slice(event);
+            return;
+        };
+        let mut other_lines = Vec::new();
+        let mut data = Vec::new();
+        for line in text.lines() {
+            match line.strip_prefix("data:") {
+                Some(rest) => data.push(rest.strip_prefix(' ').unwrap_or(rest)),
+                None if !line.is_empty() => other_lines.push(line),
+                None => {}
+            }
+        }
+        let data = data.join("\n");
+        if data.trim() == "[DONE]" {
+            self.flush_choices(out);
+            out.extend_from_slice(event);
+            return;
+        }
+        let Ok(mut json) = serde_json::from_str::<Value>(&data) else {
+            out.extend_from_slice(event);
+            return;
+        };
+
+        match self.endpoint {
+            TransformEndpoint::ChatCompletions | TransformEndpoint::Completions => {
+                self.rewrite_chunk(&mut json);
+            }
+            TransformEndpoint::Responses => self.rewrite_response_event(&mut json, out),
+            TransformEndpoint::Embeddings => {}
+        }
+
+        for line in other_lines {
+            out.extend_from_slice(line.as_bytes());
+            out.extend_from_slice(b"\n");
+        }
+        write_data(out, &json);
+    }
+
+    /// Rewrite the text of each choice in a chat completion or completion
+    /// chunk.
+    fn rewrite_chunk(&mut self, chunk: &mut Value) {
+        let chat = self.endpoint == TransformEndpoint::ChatCompletions;
+        if let Some(choices) = chunk.get_mut("choices").and_then(Value::as_array_mut) {
+            for choice in choices {
+                let index = choice.get("index").and_then(Value::as_u64).unwrap_or(0);
+                let finished = choice.get("finish_reason").is_some_and(|r| !r.is_null());
+                let stream = self
+                    .texts
+                    .entry((index, 0))
+                    .or_insert_with(|| TextStream::new(&self.steps));
+                let target = if chat {
+                    match choice.get_mut("delta") {
+                        Some(delta) => delta,
+                        None => continue,
+                    }
+                } else {
+                    choice
+                };
+                let field = if chat { "content" } else { "text" };
+                let delta = target.get(field).and_then(Value::as_str);
+                let had_text = delta.is_some();
+                let mut text = delta.map(|d| stream.push(d)).unwrap_or_default();
+                if finished {
+                    text.push_str(&stream.finish());
+                }
+                if (had_text || !text.is_empty())
+                    && let Some(target) = target.as_object_mut()
+                {
+                    target.insert(field.to_string(), Value::String(text));
+                }
+            }
+        }
+        self.last_chunk = Some(chunk.clone());
+    }
+
+    /// Send text still held back for chat completion or completion choices
+    /// as chunks of their own.
+    fn flush_choices(&mut self, out: &mut BytesMut) {
+        let Some(template) = self.last_chunk.clone() else {
+            return;
+        };
+        let chat = self.endpoint == TransformEndpoint::ChatCompletions;
+        let mut pending: Vec<_> = self
+            .texts
+            .iter_mut()
+            .map(|(&(index, _), stream)| (index, stream.finish()))
+            .filter(|(_, text)| !text.is_empty())
+            .collect();
+        pending.sort_by_key(|(index, _)| *index);
+        for (index, text) in pending {
+            let mut chunk = template.clone();
+            let Some(object) = chunk.as_object_mut() else {
+                return;
+            };
+            object.remove("usage");
+            let choice = if chat {
+                json!({"index": index, "delta": {"content": text}, "finish_reason": null})
+            } else {
+                json!({"index": index, "text": text, "finish_reason": null})
+            };
+            object.insert("choices".to_string(), json!([choice]));
+            write_data(out, &chunk);
+        }
+    }
+
+    fn rewrite_response_event(&mut self, event: &mut Value, out: &mut BytesMut) {
+        let key = (
+            event
+                .get("output_index")
+                .and_then(Value::as_u64)
+                .unwrap_or(0),
+            event
+                .get("content_index")
+                .and_then(Value::as_u64)
+                .unwrap_or(0),
+        );
+        match event.get("type").and_then(Value::as_str).unwrap_or("") {
+            "response.output_text.delta" => {
+                let stream = self
+                    .texts
+                    .entry(key)
+                    .or_insert_with(|| TextStream::new(&self.steps));
+                if let Some(Value::String(delta)) = event.get_mut("delta") {
+                    *delta = stream.push(delta);
+                }
+            }
+            "response.output_text.done" => {
+                let rest = self
+                    .texts
+                    .remove(&key)
+                    .map(|mut stream| stream.finish())
+                    .unwrap_or_default();
+                if !rest.is_empty() {
+                    let mut delta = json!({
+                        "type": "response.output_text.delta",
+                        "item_id": event.get("item_id"),
+                        "output_index": key.0,
+                        "content_index": key.1,
+                        "delta": rest,
+                    });
+                    if let Some(seq) = event.get("sequence_number") {
+                        delta["sequence_number"] = seq.clone();
+                    }
+                    out.extend_from_slice(b"event: response.output_text.delta\n");
+                    write_data(out, &delta);
+                }
+                process_field(&self.steps, event, "text");
+            }
+            "response.content_part.done" => {
+                if let Some(part) = event.get_mut("part") {
+                    process_output_part(&self.steps, part);
+                }
+            }
+            "response.output_item.done" => {
+                if let Some(item) = event.get_mut("item") {
+                    process_output_item(&self.steps, item);
+                }
+            }
+            "response.completed" | "response.incomplete" | "response.failed" => {
+                if let Some(response) = event.get_mut("response") {
+                    process_body(&self.steps, TransformEndpoint::Responses, response);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn write_data(out: &mut BytesMut, json: &Value) {
+    out.extend_from_slice(b"data: ");
+    out.extend_from_slice(json.to_string().as_bytes());
+    out.extend_from_slice(b"\n\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn steps(toml: &str) -> Vec<Arc<PostProcessor>> {
+        let config: crate::config::TransformsConfig = toml::from_str(toml).unwrap();
+        config.validate().unwrap();
+        config
+            .rules
+            .iter()
+            .map(|rule| Arc::new(PostProcessor::from_action(&rule.action).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_redact() {
+        let steps = steps(
+            r#"
+            [[rules]]
+            type = "redact"
+            patterns = ["\\b\\d{3}-\\d{2}-\\d{4}\\b", "(?i)secret-\\w+"]
+            "#,
+        );
+        assert_eq!(
+            process_text(&steps, "SSN 123-45-6789, key SECRET-abc.\nok $1"),
+            "SSN [REDACTED], key [REDACTED].\nok $1"
+        );
+    }
+
+    #[test]
+    fn test_strip_markdown() {
+        let steps = steps("[[rules]]\ntype = \"strip_markdown\"");
+        let markdown = "# Title\n\n> **Bold** and *italic* and `code`, snake_case_name\n\n- [Docs](https://example.com/docs) ![logo](https://example.com/a.png)\n---\n```rust\nlet x = *y*;\n```\n<b>done</b> ~~old~~";
+        assert_eq!(
+            process_text(&steps, markdown),
+            "Title\n\nBold and italic and code, snake_case_name\n\n- Docs (https://example.com/docs) logo\n\nlet x = *y*;\ndone old"
+        );
+    }
+
+    #[test]
+    fn test_link_policy() {
+        let steps = steps(
+            r#"
+            [[rules]]
+            type = "link_policy"
+            deny_domains = ["evil.com"]
+            rewrite_domains = { "example.com" = "mirror.example.org" }
+            "#,
+        );
+        assert_eq!(
+            process_text(
+                &steps,
+                "See https://docs.example.com/a?b=1. Avoid [this](https://www.evil.com/x) and https://evil.com, \
+                 keep https://notevil.com and [rel](/path)."
+            ),
+            "See https://docs.mirror.example.org/a?b=1. Avoid this and [link removed], \
+             keep https://notevil.com and [rel](/path)."
+        );
+
+        let allow = steps(
+            r#"
+            [[rules]]
+            type = "link_policy"
+            allow_domains = ["example.com"]
+            replacement = "[removed]"
+            "#,
+        );
+        assert_eq!(
+            process_text(&allow, "https://example.com/ok https://other.com/no"),
+            "https://example.com/ok [removed]"
+        );
+    }
+
+    #[test]
+    fn test_max_length() {
+        let steps = steps("[[rules]]\ntype = \"max_length\"\nmax_chars = 8\nsuffix = \"...\"");
+        assert_eq!(process_text(&steps, "abc\ndefghijk\nlmn"), "abc\ndefg...");
+        assert_eq!(process_text(&steps, "12345678"), "12345678");
+
+        // Streamed, the cut-off lands in the same place
+        let mut stream = TextStream::new(&steps);
+        let mut out = stream.push("ab");
+        out.push_str(&stream.push("c\ndefg"));
+        out.push_str(&stream.push("hijk\nlmn"));
+        out.push_str(&stream.finish());
+        assert_eq!(out, "abc\ndefg...");
+    }
+
+    #[test]
+    fn test_rules_compose_in_order() {
+        let steps = steps(
+            r#"
+            [[rules]]
+            type = "strip_markdown"
+
+            [[rules]]
+            type = "link_policy"
+            deny_domains = ["example.com"]
+            "#,
+        );
+        // The Markdown link becomes "text (url)" before the policy sees it
+        assert_eq!(
+            process_text(&steps, "**Read** [this](https://example.com/x)"),
+            "Read this ([link removed])"
+        );
+    }
+
+    #[test]
+    fn test_process_body() {
+        let steps = steps("[[rules]]\ntype = \"redact\"\npatterns = [\"secret\"]");
+        let mut chat = json!({"choices": [
+            {"message": {"content": "a secret"}},
+            {"message": {"content": [{"type": "text", "text": "secret"}], "tool_calls": []}}
+        ]});
+        process_body(&steps, TransformEndpoint::ChatCompletions, &mut chat);
+        assert_eq!(chat["choices"][0]["message"]["content"], "a [REDACTED]");
+        assert_eq!(
+            chat["choices"][1]["message"]["content"][0]["text"],
+            "[REDACTED]"
+        );
+
+        let mut response = json!({"output": [
+            {"type": "function_call", "arguments": "secret"},
+            {"type": "message", "content": [{"type": "output_text", "text": "my secret"}]}
+        ]});
+        process_body(&steps, TransformEndpoint::Responses, &mut response);
+        assert_eq!(response["output"][0]["arguments"], "secret");
+        assert_eq!(response["output"][1]["content"][0]["text"], "my [REDACTED]");
+    }
+
+    fn data_events(bytes: &[u8]) -> Vec<Value> {
+        std::str::from_utf8(bytes)
+            .unwrap()
+            .split("\n\n")
+            .filter_map(|event| event.lines().find_map(|l| l.strip_prefix("data: ")))
+            .filter(|data| *data != "[DONE]")
+            .map(|data| serde_json::from_str(data).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_chat_stream_redacts_across_chunks() {
+        let steps = steps("[[rules]]\ntype = \"redact\"\npatterns = [\"\\\\d{4}-\\\\d{4}\"]");
+        let mut rewriter = SseRewriter::new(TransformEndpoint::ChatCompletions, steps);
+        let chunk = |content: &str, finish: Value| {
+            format!(
+                "data: {}\n\n",
+                json!({"id": "c1", "object": "chat.completion.chunk", "model": "m",
+                       "choices": [{"index": 0, "delta": {"content": content}, "finish_reason": finish}]})
+            )
+        };
+
+        let mut out = BytesMut::new();
+        // An event split across network reads, and a number split across chunks
+        let first = chunk("Card 1234-", Value::Null);
+        out.extend_from_slice(&rewriter.push(&first.as_bytes()[..10]));
+        out.extend_from_slice(&rewriter.push(&first.as_bytes()[10..]));
+        out.extend_from_slice(&rewriter.push(chunk("5678 ok\nBye 9999-", Value::Null).as_bytes()));
+        out.extend_from_slice(&rewriter.push(chunk("0000", json!("stop")).as_bytes()));
+        out.extend_from_slice(&rewriter.push(b"data: [DONE]\n\n"));
+        out.extend_from_slice(&rewriter.finish());
+
+        let text: String = data_events(&out)
+            .iter()
+            .filter_map(|e| {
+                e["choices"][0]["delta"]["content"]
+                    .as_str()
+                    .map(String::from)
+            })
+            .collect();
+        assert_eq!(text, "Card [REDACTED] ok\nBye [REDACTED]");
+        assert!(
+            std::str::from_utf8(&out)
+                .unwrap()
+                .ends_with("data: [DONE]\n\n")
+        );
+    }
+
+    #[test]
+    fn test_chat_stream_flushes_before_done() {
+        let steps = steps("[[rules]]\ntype = \"strip_markdown\"");
+        let mut rewriter = SseRewriter::new(TransformEndpoint::ChatCompletions, steps);
+        let chunk = json!({"id": "c1", "object": "chat.completion.chunk", "model": "m",
+                           "choices": [{"index": 0, "delta": {"content": "**hi**"}, "finish_reason": null}]});
+        let mut out = BytesMut::new();
+        out.extend_from_slice(&rewriter.push(format!("data: {chunk}\n\n").as_bytes()));
+        out.extend_from_slice(&rewriter.push(b"data: [DONE]\n\n"));
+
+        let events = data_events(&out);
+        assert_eq!(events[0]["choices"][0]["delta"]["content"], "");
+        assert_eq!(events[1]["choices"][0]["delta"]["content"], "hi");
+        assert_eq!(events[1]["id"], "c1");
+    }
+
+    #[test]
+    fn test_responses_stream() {
+        let steps = steps("[[rules]]\ntype = \"redact\"\npatterns = [\"secret\"]");
+        let mut rewriter = SseRewriter::new(TransformEndpoint::Responses, steps);
+        let event = |json: Value| {
+            format!(
+                "event: {}\ndata: {json}\n\n",
+                json["type"].as_str().unwrap()
+            )
+        };
+        let mut input = String::new();
+        for delta in ["the sec", "ret is\nout"] {
+            input.push_str(&event(
+                json!({"type": "response.output_text.delta", "item_id": "msg_1",
+                "output_index": 0, "content_index": 0, "delta": delta}),
+            ));
+        }
+        input.push_str(&event(json!({"type": "response.output_text.done", "item_id": "msg_1",
+            "output_index": 0, "content_index": 0, "text": "the secret is\nout", "sequence_number": 7})));
+        input.push_str(&event(json!({"type": "response.completed", "response": {"output": [
+            {"type": "message", "content": [{"type": "output_text", "text": "the secret is\nout"}]}
+        ]}})));
+
+        let out = rewriter.push(input.as_bytes());
+        let text = std::str::from_utf8(&out).unwrap();
+        assert_eq!(
+            text.matches("event: response.output_text.delta\n").count(),
+            3
+        );
+        let events = data_events(&out);
+        let streamed: String = events
+            .iter()
+            .filter(|e| e["type"] == "response.output_text.delta")
+            .map(|e| e["delta"].as_str().unwrap())
+            .collect();
+        assert_eq!(streamed, "the [REDACTED] is\nout");
+        assert_eq!(events[3]["text"], "the [REDACTED] is\nout");
+        assert_eq!(
+            events[4]["response"]["output"][0]["content"][0]["text"],
+            "the [REDACTED] is\nout"
+        );
+    }
+}
@@ -10,6 +10,7 @@
 //! - Multiple sink support (database + OTLP simultaneously)
 //! - Graceful shutdown flushes remaining entries
 //! - **Lock-free push**: Uses crossbeam channel for contention-free writes
+//! - Optional write-ahead log so entries survive a crash (see [`crate::usage_wal`])
 //!
 //! ## Performance
 //! At high request rates, batching reduces write pressure:
@@ -64,7 +65,7 @@ impl From<&crate::config::UsageBufferConfig> for UsageBufferConfig {
 
 #[cfg(feature = "concurrency")]
 mod buffer {
-    use std::sync::Arc;
+    use std::{sync::Arc, time::Duration};
 
     use chrono::Utc;
     use crossbeam_channel::{Receiver, Sender, TrySendError};
@@ -75,8 +76,28 @@ mod buffer {
         events::{EventBus, ServerEvent},
         models::UsageLogEntry,
         usage_sink::UsageSink,
+        usage_wal::UsageWal,
     };
 
+    /// How often the write-ahead log stage checks for new entries. Entries
+    /// aren't protected until they've been written, so this is much shorter
+    /// than the flush interval.
+    const WAL_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+    /// Entries on their way into the write-ahead log.
+    ///
+    /// With a WAL, `push` sends entries here instead of to the flush
+    /// channel. A separate task writes them to the log and only then passes
+    /// them on, so nothing is flushed (and removed from the log) before it
+    /// has been written.
+    struct WalStage {
+        wal: Arc<dyn UsageWal>,
+        sender: Sender<UsageLogEntry>,
+        receiver: Receiver<UsageLogEntry>,
+        /// Set once the stage has passed on its last entry after shutdown.
+        done: std::sync::atomic::AtomicBool,
+    }
+
     /// Async buffer for usage log entries.
     ///
     /// Entries are collected and flushed to configured sinks in batches.
@@ -100,6 +121,8 @@ mod buffer {
         event_bus: Option<Arc<EventBus>>,
         /// Count of entries dropped due to buffer overflow.
         dropped_count: std::sync::atomic::AtomicU64,
+        /// Optional write-ahead log stage in front of the flush channel.
+        wal: Option<WalStage>,
     }
 
     impl UsageLogBuffer {
@@ -121,6 +144,7 @@ mod buffer {
                 shutdown: Arc::new(std::sync::atomic::AtomicBool::new(false)),
                 event_bus: None,
                 dropped_count: std::sync::atomic::AtomicU64::new(0),
+                wal: None,
             }
         }
 
@@ -140,9 +164,26 @@ mod buffer {
                 shutdown: Arc::new(std::sync::atomic::AtomicBool::new(false)),
                 event_bus: Some(event_bus),
                 dropped_count: std::sync::atomic::AtomicU64::new(0),
+                wal: None,
             }
         }
 
+        /// Write entries to a write-ahead log before flushing them.
+        ///
+        /// Entries are removed from the log once the sinks accept them, and
+        /// whatever is left is flushed when the worker starts.
+        pub fn with_wal(mut self, wal: Arc<dyn UsageWal>) -> Self {
+            let (sender, receiver) =
+                crossbeam_channel::bounded(self.sender.capacity().unwrap_or(1_000_000));
+            self.wal = Some(WalStage {
+                wal,
+                sender,
+                receiver,
+                done: std::sync::atomic::AtomicBool::new(false),
+            });
+            self
+        }
+
         /// Add a usage entry to the buffer.
         ///
         /// This is a **lock-free** operation using a crossbeam channel.
@@ -150,7 +191,11 @@ mod buffer {
         ///
         /// If the channel has exceeded `max_pending_entries`, the entry is dropped.
         pub fn push(&self, entry: UsageLogEntry) {
-            match self.sender.try_send(entry) {
+            let sender = self
+                .wal
+                .as_ref()
+                .map_or(&self.sender, |stage| &stage.sender);
+            match sender.try_send(entry) {
                 Ok(()) => {}
                 Err(TrySendError::Full(_)) => {
                     #[cfg(feature = "prometheus")]
@@ -193,6 +238,42 @@ mod buffer {
             tokio::spawn(async move {
                 let mut batch = Vec::with_capacity(max_batch_size);
 
+                // Flush what the write-ahead log kept from the last run
+                let orphan_check_interval = match &buffer.wal {
+                    Some(stage) => {
+                        match stage.wal.recover().await {
+                            Ok(entries) if !entries.is_empty() => {
+                                tracing::info!(
+                                    count = entries.len(),
+                                    wal = stage.wal.name(),
+                                    "Recovered usage entries from write-ahead log"
+                                );
+                                for chunk in entries.chunks(max_batch_size.max(1)) {
+                                    buffer.write_batch(&sink, chunk).await;
+                                }
+                            }
+                            Ok(_) => {}
+                            Err(e) => {
+                                tracing::error!(
+                                    error = %e,
+                                    wal = stage.wal.name(),
+                                    "Failed to recover usage entries from write-ahead log"
+                                );
+                            }
+                        }
+                        stage.wal.orphan_check_interval()
+                    }
+                    None => None,
+                };
+                // Only start logging new entries once recovery is done, so
+                // they aren't mistaken for leftovers and flushed twice
+                if buffer.wal.is_some() {
+                    let buffer = Arc::clone(&buffer);
+                    tokio::spawn(async move { buffer.run_wal_stage(max_batch_size).await });
+                }
+                let mut next_orphan_check =
+                    orphan_check_interval.map(|interval| tokio::time::Instant::now() + interval);
+
                 loop {
                     // Drain available entries up to batch size
                     buffer.drain_entries(&mut batch, max_batch_size);
@@ -202,8 +283,35 @@ mod buffer {
                         buffer.flush_batch(&sink, &mut batch).await;
                     }
 
-                    // Check for shutdown
-                    if buffer.shutdown.load(std::sync::atomic::Ordering::Acquire) {
+                    // Flush entries other instances left in a shared write-ahead log
+                    if let (Some(stage), Some(interval), Some(next)) =
+                        (&buffer.wal, orphan_check_interval, next_orphan_check)
+                        && tokio::time::Instant::now() >= next
+                    {
+                        match stage.wal.take_orphans().await {
+                            Ok(orphans) => {
+                                for chunk in orphans.chunks(max_batch_size.max(1)) {
+                                    buffer.write_batch(&sink, chunk).await;
+                                }
+                            }
+                            Err(e) => {
+                                tracing::warn!(
+                                    error = %e,
+                                    wal = stage.wal.name(),
+                                    "Failed to check write-ahead log for abandoned usage entries"
+                                );
+                            }
+                        }
+                        next_orphan_check = Some(tokio::time::Instant::now() + interval);
+                    }
+
+                    // Check for shutdown, once the write-ahead log stage has
+                    // passed on everything it was sent
+                    if buffer.shutdown.load(std::sync::atomic::Ordering::Acquire)
+                        && buffer.wal.as_ref().is_none_or(|stage| {
+                            stage.done.load(std::sync::atomic::Ordering::Acquire)
+                        })
+                    {
                         // Final drain and flush before exiting
                         buffer.drain_all(&mut batch);
                         if !batch.is_empty() {
@@ -228,6 +336,57 @@ mod buffer {
             })
         }
 
+        /// Write entries to the write-ahead log, then pass them on to the
+        /// flush channel. Runs until shutdown, once there's nothing left to
+        /// pass on.
+        async fn run_wal_stage(&self, max_batch_size: usize) {
+            let Some(stage) = &self.wal else { return };
+            let mut batch = Vec::with_capacity(max_batch_size);
+
+            loop {
+                while batch.len() < max_batch_size {
+                    match stage.receiver.try_recv() {
+                        Ok(entry) => batch.push(entry),
+                        Err(_) => break,
+                    }
+                }
+
+                if batch.is_empty() {
+                    if self.shutdown.load(std::sync::atomic::Ordering::Acquire) {
+                        stage.done.store(true, std::sync::atomic::Ordering::Release);
+                        break;
+                    }
+                    tokio::time::sleep(WAL_POLL_INTERVAL).await;
+                    continue;
+                }
+
+                if let Err(e) = stage.wal.append(&batch).await {
+                    // Still flush them; they just won't survive a crash
+                    tracing::error!(
+                        error = %e,
+                        count = batch.len(),
+                        wal = stage.wal.name(),
+                        "Failed to write usage entries to write-ahead log"
+                    );
+                }
+
+                let mut overflow = 0;
+                for entry in batch.drain(..) {
+                    if let Err(TrySendError::Full(_)) = self.sender.try_send(entry) {
+                        overflow += 1;
+                    }
+                }
+                if overflow > 0 {
+                    // They stay in the log and are flushed on the next start
+                    tracing::warn!(
+                        count = overflow,
+                        max_pending = self.config.max_pending_entries,
+                        "Usage buffer full: leaving entries in write-ahead log until restart"
+                    );
+                }
+            }
+        }
+
         /// Drain entries from the channel into the batch vector.
         fn drain_entries(&self, batch: &mut Vec<UsageLogEntry>, max_size: usize) {
             while batch.len() < max_size {
@@ -278,7 +437,15 @@ mod buffer {
                 }
             }
 
-            // Write to sink(s)
+            self.write_batch(sink, batch).await;
+            batch.clear();
+        }
+
+        /// Write entries to the sink, then remove them from the write-ahead
+        /// log. Entries the sink rejects stay in the log, so they're retried
+        /// on the next start.
+        async fn write_batch(&self, sink: &Arc<dyn UsageSink>, batch: &[UsageLogEntry]) {
+            let entry_count = batch.len();
             match sink.write_batch(batch).await {
                 Ok(written) => {
                     tracing::debug!(
@@ -286,6 +453,17 @@ mod buffer {
                         total = entry_count,
                         "Usage log flush successful"
                     );
+                    if let Some(stage) = &self.wal {
+                        let ids: Vec<&str> = batch.iter().map(|e| e.request_id.as_str()).collect();
+                        if let Err(e) = stage.wal.remove(&ids).await {
+                            tracing::warn!(
+                                error = %e,
+                                count = entry_count,
+                                wal = stage.wal.name(),
+                                "Failed to remove flushed usage entries from write-ahead log"
+                            );
+                        }
+                    }
                 }
                 Err(e) => {
                     tracing::error!(
@@ -295,20 +473,18 @@ mod buffer {
                     );
                 }
             }
-
-            batch.clear();
         }
 
         /// Get the current number of buffered entries.
         #[allow(dead_code)] // Used in tests; public API for buffer introspection
         pub fn len(&self) -> usize {
-            self.receiver.len()
+            self.receiver.len() + self.wal.as_ref().map_or(0, |stage| stage.receiver.len())
         }
 
         /// Check if the buffer is empty.
         #[allow(dead_code)] // Used in tests; public API for buffer introspection
         pub fn is_empty(&self) -> bool {
-            self.receiver.is_empty()
+            self.len() == 0
         }
     }
 
@@ -357,6 +533,20 @@ mod buffer {
                 tool_results_count: None,
                 tool_runtime_seconds: None,
                 tool_exit_code: None,
+                jwt_subject: None,
+                error_code: None,
+                structured_output_repairs: None,
+                context_original_tokens: None,
+                context_compressed_tokens: None,
+                degraded_from_model: None,
+                degradation_reason: None,
+                ttft_ms: None,
+                inter_token_latency_ms: None,
+                output_tokens_per_second: None,
+                idempotency_key: None,
+                provider_attempts: None,
+                suspected_duplicate_attempts: None,
+                tags: None,
             }
         }
 
@@ -471,6 +661,71 @@ mod buffer {
             assert_eq!(batch.len(), 5);
             assert_eq!(buffer.len(), 0);
         }
+
+        /// Sink that records the request IDs it was given.
+        #[cfg(feature = "server")]
+        #[derive(Default)]
+        struct RecordingSink {
+            written: crate::compat::Mutex<Vec<String>>,
+        }
+
+        #[cfg(feature = "server")]
+        #[async_trait::async_trait]
+        impl UsageSink for RecordingSink {
+            async fn write_batch(
+                &self,
+                entries: &[UsageLogEntry],
+            ) -> Result<usize, crate::usage_sink::UsageSinkError> {
+                self.written
+                    .lock()
+                    .extend(entries.iter().map(|e| e.request_id.clone()));
+                Ok(entries.len())
+            }
+
+            fn name(&self) -> &str {
+                "recording"
+            }
+        }
+
+        #[cfg(feature = "server")]
+        #[tokio::test]
+        async fn test_wal_recovers_and_removes_flushed_entries() {
+            use crate::usage_wal::FileWal;
+
+            let dir = std::env::temp_dir().join(format!("hadrian-usage-buffer-{}", Uuid::new_v4()));
+            let path = dir.join("usage.wal");
+
+            // Left over from a run that crashed before flushing
+            let leftover = make_test_entry();
+            let wal = FileWal::new(&path, false, 64).await.unwrap();
+            wal.append(std::slice::from_ref(&leftover)).await.unwrap();
+            drop(wal);
+
+            let wal: Arc<dyn UsageWal> = Arc::new(FileWal::new(&path, false, 64).await.unwrap());
+            let config = UsageBufferConfig {
+                max_size: 10,
+                flush_interval: Duration::from_millis(10),
+                max_pending_entries: 100,
+            };
+            let buffer = Arc::new(UsageLogBuffer::new(config).with_wal(wal.clone()));
+            let sink = Arc::new(RecordingSink::default());
+            let handle = buffer.start_worker(sink.clone());
+
+            let entry = make_test_entry();
+            buffer.push(entry.clone());
+            buffer.shutdown();
+            tokio::time::timeout(Duration::from_secs(5), handle)
+                .await
+                .unwrap()
+                .unwrap();
+
+            let written = sink.written.lock().clone();
+            assert_eq!(written, vec![leftover.request_id, entry.request_id]);
+            // Both were removed from the log once flushed
+            assert!(wal.recover().await.unwrap().is_empty());
+
+            let _ = tokio::fs::remove_dir_all(&dir).await;
+        }
     }
 }
 
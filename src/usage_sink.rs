@@ -545,13 +545,13 @@ impl UsageSink for CompositeSink {
             return Ok(0);
         }
 
-        let mut max_written = 0;
+        let mut max_written = None;
         let mut last_error = None;
 
         for sink in &self.sinks {
             match sink.write_batch(entries).await {
                 Ok(written) => {
-                    max_written = max_written.max(written);
+                    max_written = max_written.max(Some(written));
                     tracing::debug!(sink = sink.name(), written, "Usage sink write successful");
                 }
                 Err(e) => {
@@ -561,11 +561,12 @@ impl UsageSink for CompositeSink {
             }
         }
 
-        // Return success if at least one sink succeeded
-        if max_written > 0 {
-            Ok(max_written)
-        } else {
-            Err(last_error.unwrap_or(UsageSinkError::NotConfigured))
+        // Return success if at least one sink succeeded. A sink can succeed
+        // without writing anything, e.g. when the database already has every
+        // entry of a batch replayed from the write-ahead log.
+        match max_written {
+            Some(written) => Ok(written),
+            None => Err(last_error.unwrap_or(UsageSinkError::NotConfigured)),
         }
     }
 
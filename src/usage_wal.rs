@@ -0,0 +1,837 @@
+//! Write-ahead log for the usage buffer.
+//!
+//! The usage buffer holds entries in memory until the next flush, so a crash
+//! loses whatever hasn't reached the sinks yet. With a write-ahead log,
+//! entries are persisted before they're flushed and removed once a sink has
+//! accepted them. Whatever is left in the log on startup is flushed again.
+//!
+//! Entries are identified by their request ID. The log never hands out the
+//! same entry twice, and the database sink ignores request IDs it already
+//! has, so an entry that was flushed just before a crash isn't counted twice.
+//!
+//! ## Available Backends
+//!
+//! - **FileWal**: Append-only JSON lines file on local disk
+//! - **RedisWal**: Redis stream shared by all replicas, so surviving replicas
+//!   can flush the entries of one that never comes back
+//!
+//! ## Configuration
+//!
+//! ```toml
+//! [observability.usage.buffer.wal]
+//! type = "file"
+//! path = "data/usage.wal"
+//! ```
+
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use thiserror::Error;
+
+use crate::{config::UsageWalConfig, models::UsageLogEntry};
+
+/// Trait for usage write-ahead logs.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait UsageWal: Send + Sync {
+    /// Persist entries before they are flushed.
+    async fn append(&self, entries: &[UsageLogEntry]) -> Result<(), UsageWalError>;
+
+    /// Forget entries that the sinks have accepted.
+    async fn remove(&self, request_ids: &[&str]) -> Result<(), UsageWalError>;
+
+    /// Entries left over from a previous run, to flush on startup.
+    async fn recover(&self) -> Result<Vec<UsageLogEntry>, UsageWalError>;
+
+    /// Entries abandoned by other instances, to flush on their behalf.
+    ///
+    /// Only shared logs have any; the default returns none.
+    async fn take_orphans(&self) -> Result<Vec<UsageLogEntry>, UsageWalError> {
+        Ok(Vec::new())
+    }
+
+    /// How often to call [`UsageWal::take_orphans`], if at all.
+    fn orphan_check_interval(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Name for logging.
+    fn name(&self) -> &str;
+}
+
+/// Errors from a usage write-ahead log.
+#[derive(Debug, Error)]
+pub enum UsageWalError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Serialization error: {0}")]
+    Serialization(String),
+
+    #[cfg(feature = "redis")]
+    #[error("Redis error: {0}")]
+    Redis(#[from] redis::RedisError),
+
+    #[error("Internal error: {0}")]
+    Internal(String),
+}
+
+/// Create a write-ahead log from configuration.
+pub async fn create_usage_wal(config: &UsageWalConfig) -> Result<Arc<dyn UsageWal>, UsageWalError> {
+    let wal: Arc<dyn UsageWal> = match config {
+        #[cfg(feature = "server")]
+        UsageWalConfig::File {
+            path,
+            fsync,
+            max_file_size_mb,
+        } => Arc::new(FileWal::new(path, *fsync, *max_file_size_mb).await?),
+        #[cfg(not(feature = "server"))]
+        UsageWalConfig::File { .. } => {
+            return Err(UsageWalError::Internal(
+                "File usage WAL configured but the 'server' feature is not enabled.".to_string(),
+            ));
+        }
+
+        #[cfg(feature = "redis")]
+        UsageWalConfig::Redis {
+            url,
+            key,
+            orphan_after_secs,
+        } => Arc::new(
+            RedisWal::new(
+                url,
+                key.clone(),
+                crate::jobs::leader_lock::node_id().to_string(),
+                Duration::from_secs(*orphan_after_secs),
+            )
+            .await?,
+        ),
+        #[cfg(not(feature = "redis"))]
+        UsageWalConfig::Redis { .. } => {
+            return Err(UsageWalError::Internal(
+                "Redis usage WAL configured but the 'redis' feature is not enabled. \
+                Rebuild with: cargo build --features redis"
+                    .to_string(),
+            ));
+        }
+    };
+
+    Ok(wal)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// File WAL (requires 'server' feature)
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(feature = "server")]
+pub use file::FileWal;
+
+#[cfg(feature = "server")]
+mod file {
+    use std::{
+        collections::HashMap,
+        path::{Path, PathBuf},
+    };
+
+    use async_trait::async_trait;
+    use serde::{Deserialize, Serialize};
+    use tokio::{
+        fs::{File, OpenOptions},
+        io::AsyncWriteExt,
+        sync::Mutex,
+    };
+
+    use super::{UsageWal, UsageWalError};
+    use crate::models::UsageLogEntry;
+
+    /// A line of the log file, as written.
+    #[derive(Serialize)]
+    #[serde(rename_all = "snake_case")]
+    enum RecordRef<'a> {
+        Append(&'a UsageLogEntry),
+        Remove(&'a [&'a str]),
+    }
+
+    /// A line of the log file, as read back.
+    #[derive(Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    enum Record {
+        Append(Box<UsageLogEntry>),
+        Remove(Vec<String>),
+    }
+
+    /// File-based write-ahead log.
+    ///
+    /// Appends and removals are written as JSON lines to a single file.
+    /// Once the file outgrows `max_file_size_mb`, it is rewritten with only
+    /// the entries that haven't been removed.
+    pub struct FileWal {
+        path: PathBuf,
+        fsync: bool,
+        max_file_size: u64,
+        state: Mutex<FileState>,
+    }
+
+    struct FileState {
+        file: File,
+        /// Current size of the file in bytes.
+        size: u64,
+        /// Size right after the last rewrite, so a log that is mostly live
+        /// entries (e.g. while the sinks are down) isn't rewritten on every
+        /// write.
+        compacted_size: u64,
+    }
+
+    impl FileWal {
+        /// Open (or create) the log file.
+        pub async fn new(
+            path: impl AsRef<Path>,
+            fsync: bool,
+            max_file_size_mb: u64,
+        ) -> Result<Self, UsageWalError> {
+            let path = path.as_ref().to_path_buf();
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            let file = open_append(&path).await?;
+            let size = file.metadata().await?.len();
+
+            Ok(Self {
+                path,
+                fsync,
+                max_file_size: max_file_size_mb * 1024 * 1024,
+                state: Mutex::new(FileState {
+                    file,
+                    size,
+                    compacted_size: 0,
+                }),
+            })
+        }
+
+        async fn write(&self, state: &mut FileState, buf: &[u8]) -> Result<(), UsageWalError> {
+            state.file.write_all(buf).await?;
+            if self.fsync {
+                state.file.sync_data().await?;
+            }
+            state.size += buf.len() as u64;
+            Ok(())
+        }
+
+        /// Read the live entries from the file, in the order they were
+        /// appended.
+        async fn read_live(&self) -> Result<Vec<UsageLogEntry>, UsageWalError> {
+            let contents = match tokio::fs::read_to_string(&self.path).await {
+                Ok(contents) => contents,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+                Err(e) => return Err(e.into()),
+            };
+
+            let mut entries: Vec<Option<UsageLogEntry>> = Vec::new();
+            let mut positions: HashMap<String, usize> = HashMap::new();
+            for (i, line) in contents.lines().enumerate() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                match serde_json::from_str::<Record>(line) {
+                    Ok(Record::Append(entry)) => {
+                        if !positions.contains_key(&entry.request_id) {
+                            positions.insert(entry.request_id.clone(), entries.len());
+                            entries.push(Some(*entry));
+                        }
+                    }
+                    Ok(Record::Remove(ids)) => {
+                        for id in ids {
+                            if let Some(pos) = positions.remove(&id) {
+                                entries[pos] = None;
+                            }
+                        }
+                    }
+                    // The last line is torn if the process died mid-write
+                    Err(e) => tracing::warn!(
+                        path = ?self.path,
+                        line = i + 1,
+                        error = %e,
+                        "Skipping unreadable usage WAL record"
+                    ),
+                }
+            }
+
+            Ok(entries.into_iter().flatten().collect())
+        }
+
+        /// Replace the file with one holding only `entries`.
+        async fn rewrite(
+            &self,
+            state: &mut FileState,
+            entries: &[UsageLogEntry],
+        ) -> Result<(), UsageWalError> {
+            let tmp_path = self.path.with_extension("wal.tmp");
+            let mut buf = Vec::new();
+            for entry in entries {
+                encode(&mut buf, &RecordRef::Append(entry))?;
+            }
+            let mut tmp = File::create(&tmp_path).await?;
+            tmp.write_all(&buf).await?;
+            tmp.sync_all().await?;
+            drop(tmp);
+            tokio::fs::rename(&tmp_path, &self.path).await?;
+
+            state.file = open_append(&self.path).await?;
+            state.size = buf.len() as u64;
+            state.compacted_size = state.size;
+            Ok(())
+        }
+    }
+
+    async fn open_append(path: &Path) -> std::io::Result<File> {
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await
+    }
+
+    fn encode(buf: &mut Vec<u8>, record: &RecordRef<'_>) -> Result<(), UsageWalError> {
+        serde_json::to_writer(&mut *buf, record)
+            .map_err(|e| UsageWalError::Serialization(e.to_string()))?;
+        buf.push(b'\n');
+        Ok(())
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+    #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+    impl UsageWal for FileWal {
+        async fn append(&self, entries: &[UsageLogEntry]) -> Result<(), UsageWalError> {
+            if entries.is_empty() {
+                return Ok(());
+            }
+            let mut buf = Vec::new();
+            for entry in entries {
+                encode(&mut buf, &RecordRef::Append(entry))?;
+            }
+
+            let mut state = self.state.lock().await;
+            self.write(&mut state, &buf).await
+        }
+
+        async fn remove(&self, request_ids: &[&str]) -> Result<(), UsageWalError> {
+            if request_ids.is_empty() {
+                return Ok(());
+            }
+            let mut buf = Vec::new();
+            encode(&mut buf, &RecordRef::Remove(request_ids))?;
+
+            let mut state = self.state.lock().await;
+            self.write(&mut state, &buf).await?;
+
+            if state.size > self.max_file_size && state.size > state.compacted_size * 2 {
+                let live = self.read_live().await?;
+                self.rewrite(&mut state, &live).await?;
+                tracing::debug!(
+                    path = ?self.path,
+                    live = live.len(),
+                    size = state.size,
+                    "Compacted usage WAL"
+                );
+            }
+            Ok(())
+        }
+
+        async fn recover(&self) -> Result<Vec<UsageLogEntry>, UsageWalError> {
+            let mut state = self.state.lock().await;
+            let live = self.read_live().await?;
+            self.rewrite(&mut state, &live).await?;
+            Ok(live)
+        }
+
+        fn name(&self) -> &str {
+            "file"
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use chrono::Utc;
+        use uuid::Uuid;
+
+        use super::*;
+
+        fn make_entry() -> UsageLogEntry {
+            UsageLogEntry {
+                request_id: Uuid::new_v4().to_string(),
+                api_key_id: Some(Uuid::new_v4()),
+                user_id: None,
+                org_id: None,
+                project_id: None,
+                team_id: None,
+                service_account_id: None,
+                model: "test-model".to_string(),
+                provider: "test-provider".to_string(),
+                input_tokens: 100,
+                output_tokens: 50,
+                cost_microcents: Some(1000),
+                http_referer: None,
+                request_at: Utc::now(),
+                streamed: false,
+                cached_tokens: 0,
+                reasoning_tokens: 0,
+                finish_reason: Some("stop".to_string()),
+                latency_ms: Some(100),
+                cancelled: false,
+                status_code: Some(200),
+                pricing_source: crate::pricing::CostPricingSource::None,
+                image_count: None,
+                audio_seconds: None,
+                character_count: None,
+                provider_source: None,
+                record_type: "model".to_string(),
+                tool_name: None,
+                tool_query: None,
+                tool_url: None,
+                tool_bytes_fetched: None,
+                tool_results_count: None,
+                tool_runtime_seconds: None,
+                tool_exit_code: None,
+                jwt_subject: None,
+                error_code: None,
+                structured_output_repairs: None,
+                context_original_tokens: None,
+                context_compressed_tokens: None,
+                degraded_from_model: None,
+                degradation_reason: None,
+                ttft_ms: None,
+                inter_token_latency_ms: None,
+                output_tokens_per_second: None,
+                idempotency_key: None,
+                provider_attempts: None,
+                suspected_duplicate_attempts: None,
+                tags: None,
+            }
+        }
+
+        fn temp_path() -> PathBuf {
+            std::env::temp_dir()
+                .join(format!("hadrian-usage-wal-{}", Uuid::new_v4()))
+                .join("usage.wal")
+        }
+
+        #[tokio::test]
+        async fn test_recovers_entries_not_removed() {
+            let path = temp_path();
+            let wal = FileWal::new(&path, true, 64).await.unwrap();
+            let entries: Vec<_> = (0..3).map(|_| make_entry()).collect();
+            wal.append(&entries).await.unwrap();
+            wal.remove(&[entries[1].request_id.as_str()]).await.unwrap();
+            drop(wal);
+
+            // Simulate a crash mid-write
+            let mut file = open_append(&path).await.unwrap();
+            file.write_all(b"{\"append\":{\"request_").await.unwrap();
+            drop(file);
+
+            let wal = FileWal::new(&path, true, 64).await.unwrap();
+            let recovered = wal.recover().await.unwrap();
+            let ids: Vec<_> = recovered.iter().map(|e| e.request_id.as_str()).collect();
+            assert_eq!(
+                ids,
+                vec![
+                    entries[0].request_id.as_str(),
+                    entries[2].request_id.as_str()
+                ]
+            );
+
+            // Recovery rewrites the file without the removed entry and the
+            // torn line
+            let recovered = wal.recover().await.unwrap();
+            assert_eq!(recovered.len(), 2);
+            let lines = tokio::fs::read_to_string(&path).await.unwrap();
+            assert_eq!(lines.lines().count(), 2);
+
+            let _ = tokio::fs::remove_dir_all(path.parent().unwrap()).await;
+        }
+
+        #[tokio::test]
+        async fn test_duplicate_appends_recovered_once() {
+            let path = temp_path();
+            let wal = FileWal::new(&path, false, 64).await.unwrap();
+            let entry = make_entry();
+            wal.append(std::slice::from_ref(&entry)).await.unwrap();
+            wal.append(std::slice::from_ref(&entry)).await.unwrap();
+
+            let recovered = wal.recover().await.unwrap();
+            assert_eq!(recovered.len(), 1);
+
+            wal.remove(&[entry.request_id.as_str()]).await.unwrap();
+            assert!(wal.recover().await.unwrap().is_empty());
+
+            let _ = tokio::fs::remove_dir_all(path.parent().unwrap()).await;
+        }
+
+        #[tokio::test]
+        async fn test_compacts_when_over_size() {
+            let path = temp_path();
+            // A zero size limit compacts on every removal
+            let wal = FileWal::new(&path, false, 0).await.unwrap();
+            let entries: Vec<_> = (0..10).map(|_| make_entry()).collect();
+            wal.append(&entries).await.unwrap();
+            let ids: Vec<_> = entries[..9].iter().map(|e| e.request_id.as_str()).collect();
+            wal.remove(&ids).await.unwrap();
+
+            let lines = tokio::fs::read_to_string(&path).await.unwrap();
+            assert_eq!(lines.lines().count(), 1);
+            assert!(lines.contains(&entries[9].request_id));
+
+            // Appends after a rewrite go to the new file
+            let entry = make_entry();
+            wal.append(std::slice::from_ref(&entry)).await.unwrap();
+            assert_eq!(wal.recover().await.unwrap().len(), 2);
+
+            let _ = tokio::fs::remove_dir_all(path.parent().unwrap()).await;
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Redis WAL (requires 'redis' feature)
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(feature = "redis")]
+pub use self::redis_wal::RedisWal;
+
+#[cfg(feature = "redis")]
+mod redis_wal {
+    use std::{collections::HashMap, time::Duration};
+
+    use async_trait::async_trait;
+    use redis::Value;
+
+    use super::{UsageWal, UsageWalError};
+    use crate::{compat::Mutex, models::UsageLogEntry};
+
+    /// Number of stream entries read per XRANGE call.
+    const PAGE_SIZE: usize = 1000;
+
+    /// An entry read back from the stream.
+    struct StreamEntry {
+        stream_id: String,
+        node: String,
+        entry: UsageLogEntry,
+    }
+
+    /// Redis Streams-based write-ahead log.
+    ///
+    /// All replicas append to one stream, tagging entries with their node ID
+    /// (`HADRIAN_NODE_ID`, else `HOSTNAME`). A replica recovers its own
+    /// entries when it restarts. Entries of other nodes still in the stream
+    /// after `orphan_after` belong to a replica that stopped without flushing
+    /// them, and are taken over by whichever replica claims them first.
+    pub struct RedisWal {
+        client: redis::Client,
+        key: String,
+        node: String,
+        orphan_after: Duration,
+        /// Stream IDs of the entries this node holds, by request ID.
+        stream_ids: Mutex<HashMap<String, String>>,
+    }
+
+    impl RedisWal {
+        /// Connect to Redis and verify connectivity.
+        pub async fn new(
+            url: &str,
+            key: String,
+            node: String,
+            orphan_after: Duration,
+        ) -> Result<Self, UsageWalError> {
+            let client = redis::Client::open(url)?;
+            let mut conn = client.get_multiplexed_async_connection().await?;
+            let _: String = redis::cmd("PING").query_async(&mut conn).await?;
+
+            Ok(Self {
+                client,
+                key,
+                node,
+                orphan_after,
+                stream_ids: Mutex::new(HashMap::new()),
+            })
+        }
+
+        async fn conn(&self) -> Result<redis::aio::MultiplexedConnection, UsageWalError> {
+            Ok(self.client.get_multiplexed_async_connection().await?)
+        }
+
+        /// Add an entry to the stream under this node, returning its stream ID.
+        async fn add(
+            &self,
+            conn: &mut redis::aio::MultiplexedConnection,
+            entry: &UsageLogEntry,
+        ) -> Result<String, UsageWalError> {
+            let json = serde_json::to_string(entry)
+                .map_err(|e| UsageWalError::Serialization(e.to_string()))?;
+            Ok(redis::cmd("XADD")
+                .arg(&self.key)
+                .arg("*")
+                .arg("id")
+                .arg(&entry.request_id)
+                .arg("node")
+                .arg(&self.node)
+                .arg("entry")
+                .arg(json)
+                .query_async(conn)
+                .await?)
+        }
+
+        /// Read stream entries with IDs up to `end` (`+` for all).
+        async fn read_until(
+            &self,
+            conn: &mut redis::aio::MultiplexedConnection,
+            end: &str,
+        ) -> Result<Vec<StreamEntry>, UsageWalError> {
+            let mut entries = Vec::new();
+            let mut start = "-".to_string();
+            loop {
+                let page: Value = redis::cmd("XRANGE")
+                    .arg(&self.key)
+                    .arg(&start)
+                    .arg(end)
+                    .arg("COUNT")
+                    .arg(PAGE_SIZE)
+                    .query_async(conn)
+                    .await?;
+                let (last_id, page) = parse_xrange(page);
+                let done = page.len() < PAGE_SIZE;
+                entries.extend(page);
+                match last_id {
+                    Some(id) if !done => start = format!("({id}"),
+                    _ => break,
+                }
+            }
+            Ok(entries)
+        }
+
+        /// Keep the first stream entry of each request ID, deleting the rest.
+        async fn dedup(
+            &self,
+            conn: &mut redis::aio::MultiplexedConnection,
+            entries: Vec<StreamEntry>,
+        ) -> Result<Vec<StreamEntry>, UsageWalError> {
+            let mut seen = std::collections::HashSet::new();
+            let mut unique = Vec::with_capacity(entries.len());
+            let mut duplicates = Vec::new();
+            for entry in entries {
+                if seen.insert(entry.entry.request_id.clone()) {
+                    unique.push(entry);
+                } else {
+                    duplicates.push(entry.stream_id);
+                }
+            }
+            if !duplicates.is_empty() {
+                let _: u64 = redis::cmd("XDEL")
+                    .arg(&self.key)
+                    .arg(&duplicates)
+                    .query_async(conn)
+                    .await?;
+            }
+            Ok(unique)
+        }
+
+        /// Move another node's entry to this node.
+        ///
+        /// The copy is added before the original is deleted, so the entry is
+        /// never missing from the stream. Only the replica whose delete
+        /// removes the original keeps its copy.
+        async fn claim(
+            &self,
+            conn: &mut redis::aio::MultiplexedConnection,
+            orphan: &StreamEntry,
+        ) -> Result<Option<String>, UsageWalError> {
+            let stream_id = self.add(conn, &orphan.entry).await?;
+            let deleted: u64 = redis::cmd("XDEL")
+                .arg(&self.key)
+                .arg(&orphan.stream_id)
+                .query_async(conn)
+                .await?;
+            if deleted == 1 {
+                return Ok(Some(stream_id));
+            }
+            let _: u64 = redis::cmd("XDEL")
+                .arg(&self.key)
+                .arg(&stream_id)
+                .query_async(conn)
+                .await?;
+            Ok(None)
+        }
+
+        /// Stream ID of the newest entry old enough to be an orphan.
+        fn orphan_cutoff(&self) -> String {
+            let cutoff = chrono::Utc::now()
+                - chrono::Duration::from_std(self.orphan_after).unwrap_or_default();
+            cutoff.timestamp_millis().max(0).to_string()
+        }
+    }
+
+    /// Parse an XRANGE reply, returning the last stream ID read and the
+    /// entries that could be decoded.
+    fn parse_xrange(value: Value) -> (Option<String>, Vec<StreamEntry>) {
+        let mut last_id = None;
+        let mut entries = Vec::new();
+
+        let Value::Array(items) = value else {
+            return (last_id, entries);
+        };
+        for item in items {
+            let Value::Array(item) = item else { continue };
+            let (Some(Value::BulkString(id)), Some(Value::Array(fields))) =
+                (item.first(), item.get(1))
+            else {
+                continue;
+            };
+            let stream_id = String::from_utf8_lossy(id).to_string();
+            last_id = Some(stream_id.clone());
+
+            let mut node = None;
+            let mut entry = None;
+            let mut iter = fields.iter();
+            while let (Some(Value::BulkString(k)), Some(Value::BulkString(v))) =
+                (iter.next(), iter.next())
+            {
+                match k.as_slice() {
+                    b"node" => node = Some(String::from_utf8_lossy(v).to_string()),
+                    b"entry" => match serde_json::from_slice::<UsageLogEntry>(v) {
+                        Ok(e) => entry = Some(e),
+                        Err(e) => tracing::warn!(
+                            stream_id,
+                            error = %e,
+                            "Skipping unreadable usage WAL entry"
+                        ),
+                    },
+                    _ => {}
+                }
+            }
+            if let (Some(node), Some(entry)) = (node, entry) {
+                entries.push(StreamEntry {
+                    stream_id,
+                    node,
+                    entry,
+                });
+            }
+        }
+
+        (last_id, entries)
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+    #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+    impl UsageWal for RedisWal {
+        async fn append(&self, entries: &[UsageLogEntry]) -> Result<(), UsageWalError> {
+            if entries.is_empty() {
+                return Ok(());
+            }
+            let mut conn = self.conn().await?;
+            let mut pipe = redis::pipe();
+            for entry in entries {
+                let json = serde_json::to_string(entry)
+                    .map_err(|e| UsageWalError::Serialization(e.to_string()))?;
+                pipe.cmd("XADD")
+                    .arg(&self.key)
+                    .arg("*")
+                    .arg("id")
+                    .arg(&entry.request_id)
+                    .arg("node")
+                    .arg(&self.node)
+                    .arg("entry")
+                    .arg(json);
+            }
+            let stream_ids: Vec<String> = pipe.query_async(&mut conn).await?;
+
+            let mut map = self.stream_ids.lock();
+            for (entry, stream_id) in entries.iter().zip(stream_ids) {
+                map.insert(entry.request_id.clone(), stream_id);
+            }
+            Ok(())
+        }
+
+        async fn remove(&self, request_ids: &[&str]) -> Result<(), UsageWalError> {
+            let stream_ids: Vec<String> = {
+                let mut map = self.stream_ids.lock();
+                request_ids
+                    .iter()
+                    .filter_map(|id| map.remove(*id))
+                    .collect()
+            };
+            if stream_ids.is_empty() {
+                return Ok(());
+            }
+            let mut conn = self.conn().await?;
+            let _: u64 = redis::cmd("XDEL")
+                .arg(&self.key)
+                .arg(&stream_ids)
+                .query_async(&mut conn)
+                .await?;
+            Ok(())
+        }
+
+        async fn recover(&self) -> Result<Vec<UsageLogEntry>, UsageWalError> {
+            let mut conn = self.conn().await?;
+            let own: Vec<_> = self
+                .read_until(&mut conn, "+")
+                .await?
+                .into_iter()
+                .filter(|e| e.node == self.node)
+                .collect();
+            let own = self.dedup(&mut conn, own).await?;
+
+            let mut map = self.stream_ids.lock();
+            Ok(own
+                .into_iter()
+                .map(|e| {
+                    map.insert(e.entry.request_id.clone(), e.stream_id);
+                    e.entry
+                })
+                .collect())
+        }
+
+        async fn take_orphans(&self) -> Result<Vec<UsageLogEntry>, UsageWalError> {
+            let mut conn = self.conn().await?;
+            let cutoff = self.orphan_cutoff();
+            let orphans: Vec<_> = self
+                .read_until(&mut conn, &cutoff)
+                .await?
+                .into_iter()
+                .filter(|e| e.node != self.node)
+                .collect();
+
+            let mut taken = Vec::new();
+            for orphan in orphans {
+                let request_id = orphan.entry.request_id.clone();
+                if self.stream_ids.lock().contains_key(&request_id) {
+                    // Already holding this entry; just drop the other copy
+                    let _: u64 = redis::cmd("XDEL")
+                        .arg(&self.key)
+                        .arg(&orphan.stream_id)
+                        .query_async(&mut conn)
+                        .await?;
+                    continue;
+                }
+                if let Some(stream_id) = self.claim(&mut conn, &orphan).await? {
+                    self.stream_ids.lock().insert(request_id, stream_id);
+                    taken.push(orphan.entry);
+                }
+            }
+            if !taken.is_empty() {
+                tracing::info!(
+                    count = taken.len(),
+                    "Took over usage entries left in the WAL by other replicas"
+                );
+            }
+            Ok(taken)
+        }
+
+        fn orphan_check_interval(&self) -> Option<Duration> {
+            Some(self.orphan_after.max(Duration::from_secs(1)))
+        }
+
+        fn name(&self) -> &str {
+            "redis"
+        }
+    }
+}
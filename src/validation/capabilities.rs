@@ -0,0 +1,340 @@
+//! Request capability checks against model metadata.
+//!
+//! Requests that use a feature the target model lacks (tools, image or audio
+//! input, JSON mode, structured output) are rejected before they reach the provider, so
+//! clients get a 400 naming the parameter instead of an opaque upstream error.
+//! Capabilities come from the model's `capabilities`/`modalities` config,
+//! falling back to the model catalog. Models without either are not checked.
+
+use crate::{
+    api_types::{
+        CreateChatCompletionPayload, CreateResponsesPayload,
+        chat_completion::{ContentPart, Message, MessageContent, ResponseFormat},
+        responses::{
+            EasyInputMessageContent, ResponseFormatTextConfig, ResponseInputContentItem,
+            ResponsesInput, ResponsesInputItem,
+        },
+    },
+    catalog::{ModelCapabilities, ModelModalities},
+};
+
+/// API a request was made to, which decides the parameter names in errors.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RequestApi {
+    #[default]
+    ChatCompletions,
+    Responses,
+}
+
+/// Features a request uses that not every model supports.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RequestFeatures {
+    pub api: RequestApi,
+    /// `tools` is set and non-empty
+    pub tools: bool,
+    /// Image content, or file content for the Responses API
+    pub images: bool,
+    pub audio: bool,
+    pub video: bool,
+    /// `parallel_tool_calls` is explicitly `true`
+    pub parallel_tool_calls: bool,
+    /// `response_format` (Chat Completions) or `text.format` (Responses) is
+    /// `json_object`
+    pub json_mode: bool,
+    /// `response_format` or `text.format` is `json_schema`
+    pub structured_output: bool,
+}
+
+impl RequestFeatures {
+    /// Features used by a Chat Completions request.
+    pub fn for_chat(payload: &CreateChatCompletionPayload) -> Self {
+        let mut features = Self {
+            tools: payload.tools.as_ref().is_some_and(|t| !t.is_empty()),
+            json_mode: matches!(payload.response_format, Some(ResponseFormat::JsonObject)),
+            structured_output: matches!(
+                payload.response_format,
+                Some(ResponseFormat::JsonSchema { .. })
+            ),
+            ..Default::default()
+        };
+
+        for message in &payload.messages {
+            let content = match message {
+                Message::System { content, .. }
+                | Message::User { content, .. }
+                | Message::Tool { content, .. }
+                | Message::Developer { content, .. } => content,
+                Message::Assistant { content, .. } => match content {
+                    Some(content) => content,
+                    None => continue,
+                },
+            };
+            let MessageContent::Parts(parts) = content else {
+                continue;
+            };
+            for part in parts {
+                match part {
+                    ContentPart::ImageUrl { .. } => features.images = true,
+                    ContentPart::InputAudio { .. } => features.audio = true,
+                    ContentPart::InputVideo { .. } | ContentPart::VideoUrl { .. } => {
+                        features.video = true
+                    }
+                    _ => {}
+                }
+            }
+        }
+        features
+    }
+
+    /// Features used by a Responses API request.
+    pub fn for_responses(payload: &CreateResponsesPayload) -> Self {
+        let format = payload.text.as_ref().and_then(|t| t.format.as_ref());
+        let mut features = Self {
+            api: RequestApi::Responses,
+            tools: payload.tools.as_ref().is_some_and(|t| !t.is_empty()),
+            parallel_tool_calls: payload.parallel_tool_calls == Some(true),
+            json_mode: matches!(format, Some(ResponseFormatTextConfig::JsonObject)),
+            structured_output: matches!(format, Some(ResponseFormatTextConfig::JsonSchema { .. })),
+            ..Default::default()
+        };
+
+        let Some(ResponsesInput::Items(items)) = &payload.input else {
+            return features;
+        };
+        for item in items {
+            let parts = match item {
+                ResponsesInputItem::EasyMessage(message) => match &message.content {
+                    EasyInputMessageContent::Parts(parts) => parts.as_slice(),
+                    EasyInputMessageContent::Text(_) => continue,
+                },
+                ResponsesInputItem::MessageItem(message) => message.content.as_slice(),
+                _ => continue,
+            };
+            for part in parts {
+                match part {
+                    ResponseInputContentItem::InputImage { .. }
+                    | ResponseInputContentItem::InputFile { .. } => features.images = true,
+                    ResponseInputContentItem::InputAudio { .. } => features.audio = true,
+                    ResponseInputContentItem::InputText { .. } => {}
+                }
+            }
+        }
+        features
+    }
+}
+
+/// A feature the request uses that the model does not support.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsupportedFeature {
+    /// Request parameter that uses the feature
+    pub param: &'static str,
+    pub message: String,
+}
+
+/// Check the features a request uses against a model's capabilities.
+///
+/// Missing `capabilities` skip the tool, image and JSON output checks;
+/// an empty `modalities.input` list skips the audio and video checks.
+pub fn check_features(
+    features: RequestFeatures,
+    model: &str,
+    capabilities: Option<&ModelCapabilities>,
+    modalities: Option<&ModelModalities>,
+) -> Result<(), UnsupportedFeature> {
+    let unsupported = |param: &'static str, what: &str| UnsupportedFeature {
+        param,
+        message: format!("Model '{model}' does not support {what}"),
+    };
+    let (content_param, format_param) = match features.api {
+        RequestApi::ChatCompletions => ("messages", "response_format"),
+        RequestApi::Responses => ("input", "text.format"),
+    };
+
+    if let Some(caps) = capabilities {
+        if features.tools && !caps.tool_call {
+            return Err(unsupported("tools", "tool calling"));
+        }
+        if features.tools && features.parallel_tool_calls && caps.parallel_tool_calls == Some(false)
+        {
+            return Err(unsupported("parallel_tool_calls", "parallel tool calls"));
+        }
+        if features.images && !caps.vision {
+            return Err(unsupported(content_param, "image or file input"));
+        }
+        if features.json_mode && !caps.supports_json_mode() {
+            return Err(unsupported(format_param, "JSON mode"));
+        }
+        if features.structured_output && !caps.structured_output {
+            return Err(unsupported(format_param, "structured output"));
+        }
+    }
+
+    if let Some(mods) = modalities.filter(|m| !m.input.is_empty()) {
+        let accepts = |modality: &str| mods.input.iter().any(|m| m == modality);
+        if features.audio && !accepts("audio") {
+            return Err(unsupported(content_param, "audio input"));
+        }
+        if features.video && !accepts("video") {
+            return Err(unsupported(content_param, "video input"));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn caps(tool_call: bool, vision: bool, structured_output: bool) -> ModelCapabilities {
+        ModelCapabilities {
+            vision,
+            reasoning: false,
+            tool_call,
+            structured_output,
+            temperature: true,
+            json_mode: None,
+            parallel_tool_calls: None,
+        }
+    }
+
+    fn text_only() -> ModelModalities {
+        ModelModalities {
+            input: vec!["text".to_string()],
+            output: vec!["text".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_chat_features() {
+        let payload: CreateChatCompletionPayload = serde_json::from_value(json!({
+            "model": "gpt-4o",
+            "messages": [{
+                "role": "user",
+                "content": [
+                    {"type": "text", "text": "What is this?"},
+                    {"type": "image_url", "image_url": {"url": "https://example.com/a.png"}}
+                ]
+            }],
+            "tools": [{"type": "function", "function": {"name": "lookup"}}],
+            "response_format": {"type": "json_object"}
+        }))
+        .unwrap();
+
+        let features = RequestFeatures::for_chat(&payload);
+        assert!(features.tools && features.images && features.json_mode);
+        assert!(!features.audio && !features.video && !features.structured_output);
+    }
+
+    #[test]
+    fn test_responses_features() {
+        let payload: CreateResponsesPayload = serde_json::from_value(json!({
+            "model": "gpt-4o",
+            "input": [{
+                "role": "user",
+                "content": [
+                    {"type": "input_text", "text": "Summarize"},
+                    {"type": "input_file", "file_id": "file-abc"}
+                ]
+            }],
+            "text": {"format": {"type": "text"}}
+        }))
+        .unwrap();
+
+        let features = RequestFeatures::for_responses(&payload);
+        assert!(features.images);
+        assert!(!features.tools && !features.structured_output);
+    }
+
+    #[test]
+    fn test_check_features_names_parameter() {
+        let tools = RequestFeatures {
+            tools: true,
+            ..Default::default()
+        };
+        let err =
+            check_features(tools, "o1-mini", Some(&caps(false, true, true)), None).unwrap_err();
+        assert_eq!(err.param, "tools");
+        assert_eq!(err.message, "Model 'o1-mini' does not support tool calling");
+
+        let images = RequestFeatures {
+            images: true,
+            ..Default::default()
+        };
+        let err = check_features(images, "m", Some(&caps(true, false, true)), None).unwrap_err();
+        assert_eq!(err.param, "messages");
+
+        let json = RequestFeatures {
+            structured_output: true,
+            ..Default::default()
+        };
+        let err = check_features(json, "m", Some(&caps(true, true, false)), None).unwrap_err();
+        assert_eq!(err.param, "response_format");
+
+        let json = RequestFeatures {
+            api: RequestApi::Responses,
+            ..json
+        };
+        let err = check_features(json, "m", Some(&caps(true, true, false)), None).unwrap_err();
+        assert_eq!(err.param, "text.format");
+    }
+
+    #[test]
+    fn test_check_features_json_mode_and_parallel_tool_calls() {
+        let json_mode = RequestFeatures {
+            json_mode: true,
+            ..Default::default()
+        };
+        // Unknown JSON mode support falls back to structured output
+        assert!(check_features(json_mode, "m", Some(&caps(true, true, true)), None).is_ok());
+        let mut no_json_mode = caps(true, true, true);
+        no_json_mode.json_mode = Some(false);
+        let err = check_features(json_mode, "m", Some(&no_json_mode), None).unwrap_err();
+        assert_eq!(err.message, "Model 'm' does not support JSON mode");
+
+        let parallel = RequestFeatures {
+            api: RequestApi::Responses,
+            tools: true,
+            parallel_tool_calls: true,
+            ..Default::default()
+        };
+        assert!(check_features(parallel, "m", Some(&caps(true, true, true)), None).is_ok());
+        let mut sequential = caps(true, true, true);
+        sequential.parallel_tool_calls = Some(false);
+        let err = check_features(parallel, "m", Some(&sequential), None).unwrap_err();
+        assert_eq!(err.param, "parallel_tool_calls");
+    }
+
+    #[test]
+    fn test_check_features_uses_input_modalities() {
+        let audio = RequestFeatures {
+            audio: true,
+            ..Default::default()
+        };
+        assert!(check_features(audio, "m", None, Some(&text_only())).is_err());
+
+        let mut with_audio = text_only();
+        with_audio.input.push("audio".to_string());
+        assert!(check_features(audio, "m", None, Some(&with_audio)).is_ok());
+
+        // No declared input modalities means nothing to check against
+        assert!(check_features(audio, "m", None, Some(&ModelModalities::default())).is_ok());
+    }
+
+    #[test]
+    fn test_check_features_without_metadata_passes() {
+        let everything = RequestFeatures {
+            api: RequestApi::ChatCompletions,
+            tools: true,
+            images: true,
+            audio: true,
+            video: true,
+            parallel_tool_calls: true,
+            json_mode: true,
+            structured_output: true,
+        };
+        assert!(check_features(everything, "m", None, None).is_ok());
+    }
+}
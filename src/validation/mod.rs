@@ -24,6 +24,7 @@
 //! }
 //! ```
 
+pub mod capabilities;
 mod schema;
 pub mod stream;
 pub mod url;
@@ -133,6 +133,13 @@ impl HadrianGateway {
             dlq: None,
             pricing: Arc::new(config.pricing.clone()),
             circuit_breakers: providers::CircuitBreakerRegistry::new(),
+            fair_queues: providers::FairQueueRegistry::new(),
+            adaptive_limiters: providers::AdaptiveConcurrencyRegistry::new(),
+            provider_settings: providers::ProviderSettingsRegistry::new(
+                providers::CircuitBreakerRegistry::new(),
+                providers::FairQueueRegistry::new(),
+            ),
+            token_buckets: None,
             provider_health: jobs::ProviderHealthStateRegistry::new(),
             #[cfg(feature = "sso")]
             oidc_registry: None,
@@ -140,11 +147,18 @@ impl HadrianGateway {
             saml_registry: None,
             #[cfg(feature = "jwt")]
             gateway_jwt_registry: None,
+            #[cfg(feature = "jwt")]
+            bearer_jwt: None,
             policy_registry: None,
+            request_policies: None,
             response_cache: None,
+            embeddings_cache: None,
             semantic_cache: None,
             input_guardrails: None,
             output_guardrails: None,
+            token_counter: None,
+            embedding_batcher: None,
+            email_notifier: None,
             event_bus,
             file_search_service: None,
             #[cfg(any(
@@ -491,11 +505,14 @@ fn wasm_default_config() -> config::GatewayConfig {
                     models: HashMap::new(),
                     retry: config::RetryConfig::default(),
                     circuit_breaker: config::CircuitBreakerConfig::default(),
+                    fair_queue: None,
+                    adaptive_concurrency: None,
                     fallback_providers: Vec::new(),
                     model_fallbacks: HashMap::new(),
                     health_check: config::ProviderHealthCheckConfig::default(),
                     catalog_provider: None,
                     sovereignty: None,
+                    max_response_bytes: None,
                 }),
             )]),
         },
@@ -540,5 +557,7 @@ fn wasm_default_config() -> config::GatewayConfig {
         retention: config::RetentionConfig::default(),
         storage: config::StorageConfig::default(),
         sovereignty: config::SovereigntyConfig::default(),
+        notifications: config::NotificationsConfig::default(),
+        federation: config::FederationConfig::default(),
     }
 }
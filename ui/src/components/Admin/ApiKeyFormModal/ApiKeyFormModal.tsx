@@ -26,18 +26,7 @@ import {
   buildSovereigntyRequirements,
   SovereigntyFormFields,
 } from "./sovereigntyFields";
-
-// Available API key scopes
-const API_KEY_SCOPES = [
-  { value: "chat", label: "Chat" },
-  { value: "completions", label: "Completions" },
-  { value: "embeddings", label: "Embeddings" },
-  { value: "images", label: "Images" },
-  { value: "audio", label: "Audio" },
-  { value: "files", label: "Files" },
-  { value: "models", label: "Models" },
-  { value: "admin", label: "Admin" },
-];
+import { API_KEY_SCOPE_OPTIONS } from "./apiKeyOptionsFields";
 
 // Validation for model patterns (supports wildcards like "gpt-4*")
 const MODEL_PATTERN_REGEX = /^[a-zA-Z0-9][a-zA-Z0-9\-._/]*\*?$/;
@@ -479,7 +468,7 @@ export function ApiKeyFormModal({
                         render={({ field }) => (
                           <Select
                             multiple
-                            options={API_KEY_SCOPES}
+                            options={API_KEY_SCOPE_OPTIONS}
                             value={field.value || []}
                             onChange={field.onChange}
                             placeholder="Select scopes..."
@@ -35,6 +35,50 @@ export const API_KEY_SCOPES = [
   { value: "admin", label: "Admin", description: "Full access to /admin endpoints" },
 ];
 
+/** Narrower `<scope>:read|write` and `admin:<area>` forms of the scopes above. */
+export const API_KEY_FINE_GRAINED_SCOPES = [
+  {
+    value: "chat:write",
+    label: "Chat (write)",
+    description: "Create chat completions and responses",
+  },
+  { value: "embeddings:write", label: "Embeddings (write)", description: "Generate embeddings" },
+  {
+    value: "files:read",
+    label: "Files (read)",
+    description: "List and download files and vector stores",
+  },
+  {
+    value: "files:write",
+    label: "Files (write)",
+    description: "Upload, modify, delete files and vector stores",
+  },
+  { value: "models:read", label: "Models (read)", description: "List available models" },
+  {
+    value: "admin:read",
+    label: "Admin (read)",
+    description: "Read-only access to /admin endpoints",
+  },
+  {
+    value: "admin:usage:read",
+    label: "Admin usage (read)",
+    description: "Read usage and spend reports",
+  },
+  { value: "admin:api-keys", label: "Admin API keys", description: "Manage API keys" },
+  {
+    value: "admin:service-accounts",
+    label: "Admin service accounts",
+    description: "Manage service accounts",
+  },
+  {
+    value: "admin:audit-logs:read",
+    label: "Admin audit logs (read)",
+    description: "Read audit logs",
+  },
+];
+
+export const API_KEY_SCOPE_OPTIONS = [...API_KEY_SCOPES, ...API_KEY_FINE_GRAINED_SCOPES];
+
 const MODEL_PATTERN_REGEX = /^[a-zA-Z0-9][a-zA-Z0-9\-._/]*\*?$/;
 
 export function validateModelPatterns(value: string | undefined): boolean {
@@ -307,7 +351,7 @@ export function ApiKeyOptionsFields({
                   render={({ field }) => (
                     <Select
                       multiple
-                      options={API_KEY_SCOPES}
+                      options={API_KEY_SCOPE_OPTIONS}
                       value={field.value || []}
                       onChange={field.onChange}
                       placeholder="Select scopes..."
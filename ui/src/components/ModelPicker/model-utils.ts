@@ -7,6 +7,10 @@ export interface ModelCapabilities {
   tool_call: boolean;
   structured_output: boolean;
   temperature: boolean;
+  /** JSON mode support; falls back to `structured_output` when absent */
+  json_mode?: boolean;
+  /** Whether the model can call several tools in one turn, when known */
+  parallel_tool_calls?: boolean;
 }
 
 /** Model modalities from the catalog */
@@ -69,6 +73,10 @@ export interface ModelInfo {
   knowledge_cutoff?: string;
   /** Release date (ISO format or YYYY-MM-DD) */
   release_date?: string;
+  /** Date the provider deprecated the model (YYYY-MM-DD) */
+  deprecation_date?: string;
+  /** Date the provider stops serving the model (YYYY-MM-DD) */
+  sunset_date?: string;
   /** Supported image sizes for image generation models */
   image_sizes?: string[];
   /** Supported image quality options for image generation models */
@@ -122,6 +122,14 @@ export interface ChatConfig {
   file_uploads_enabled: boolean;
   max_file_size_bytes: number;
   allowed_file_types: string[];
+  /** Org/project request defaults, returned when `?org=` is passed */
+  presets?: ChatPresets | null;
+}
+
+export interface ChatPresets {
+  default_model: string | null;
+  temperature: number | null;
+  has_system_prompt: boolean;
 }
 
 export interface AdminConfig {
@@ -17,6 +17,7 @@ import { FormField } from "@/components/FormField/FormField";
 import { Select } from "@/components/Select/Select";
 import {
   API_KEY_SCOPES,
+  API_KEY_SCOPE_OPTIONS,
   ApiKeyOptionsFields,
   type ApiKeyOptionsFormValues,
   buildApiKeyOptionsPayload,
@@ -435,7 +436,7 @@ export default function OAuthAuthorizePage() {
                   render={({ field }) => (
                     <Select
                       multiple
-                      options={API_KEY_SCOPES}
+                      options={API_KEY_SCOPE_OPTIONS}
                       value={field.value || []}
                       onChange={field.onChange}
                       placeholder="Select scopes..."
@@ -456,7 +457,11 @@ export default function OAuthAuthorizePage() {
                     </p>
                     <ul className="mt-2 grid grid-cols-1 gap-x-4 gap-y-1 text-xs sm:grid-cols-2">
                       {API_KEY_SCOPES.map((s) => {
-                        const granted = grantsAll || selectedScopes.includes(s.value);
+                        const granted =
+                          grantsAll ||
+                          selectedScopes.some(
+                            (v) => v === s.value || v.startsWith(`${s.value}:`)
+                          );
                         return (
                           <li key={s.value} className="flex items-start gap-2">
                             {granted ? (
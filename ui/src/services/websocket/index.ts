@@ -52,6 +52,7 @@ export type {
   HealthEvent,
   ProviderHealthChangedEvent,
   CircuitBreakerStateChangedEvent,
+  ProviderRoutingWeightChangedEvent,
   AuditLogCreatedEvent,
   UsageRecordedEvent,
   BudgetThresholdReachedEvent,
@@ -62,6 +63,7 @@ export type {
 export {
   isProviderHealthChangedEvent,
   isCircuitBreakerStateChangedEvent,
+  isProviderRoutingWeightChangedEvent,
   isHealthEvent,
   isAuditLogCreatedEvent,
   isUsageRecordedEvent,
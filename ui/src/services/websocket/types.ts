@@ -127,6 +127,16 @@ export interface ProviderHealthChangedEvent {
   error_message?: string;
 }
 
+/** Provider health-based routing weight changed event */
+export interface ProviderRoutingWeightChangedEvent {
+  event_type: "provider_routing_weight_changed";
+  provider: string;
+  timestamp: string;
+  previous_weight: number;
+  weight: number;
+  reason: "unhealthy" | "degraded" | "recovering";
+}
+
 /** Circuit breaker state changed event */
 export interface CircuitBreakerStateChangedEvent {
   event_type: "circuit_breaker_state_changed";
@@ -193,12 +203,16 @@ export interface RateLimitWarningEvent {
 }
 
 /** Union of all health-related events */
-export type HealthEvent = ProviderHealthChangedEvent | CircuitBreakerStateChangedEvent;
+export type HealthEvent =
+  | ProviderHealthChangedEvent
+  | CircuitBreakerStateChangedEvent
+  | ProviderRoutingWeightChangedEvent;
 
 /** Union of all server events */
 export type ServerEvent =
   | ProviderHealthChangedEvent
   | CircuitBreakerStateChangedEvent
+  | ProviderRoutingWeightChangedEvent
   | AuditLogCreatedEvent
   | UsageRecordedEvent
   | BudgetThresholdReachedEvent
@@ -222,9 +236,20 @@ export function isCircuitBreakerStateChangedEvent(
   return event.event_type === "circuit_breaker_state_changed";
 }
 
+/** Check if an event is a provider routing weight changed event */
+export function isProviderRoutingWeightChangedEvent(
+  event: ServerEvent
+): event is ProviderRoutingWeightChangedEvent {
+  return event.event_type === "provider_routing_weight_changed";
+}
+
 /** Check if an event is a health event */
 export function isHealthEvent(event: ServerEvent): event is HealthEvent {
-  return isProviderHealthChangedEvent(event) || isCircuitBreakerStateChangedEvent(event);
+  return (
+    isProviderHealthChangedEvent(event) ||
+    isCircuitBreakerStateChangedEvent(event) ||
+    isProviderRoutingWeightChangedEvent(event)
+  );
 }
 
 /** Check if an event is an audit log created event */